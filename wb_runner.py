@@ -3,7 +3,7 @@
 # This script is part of the WhiteboxTools geospatial analysis library.
 # Authors: Dr. John Lindsay, Rachel Broders
 # Created: 28/11/2017
-# Last Modified: 05/11/2019
+# Last Modified: 16/10/2026
 # License: MIT
 
 import __future__
@@ -21,11 +21,12 @@ from pathlib import Path
 import glob
 from sys import platform as _platform
 import shlex
+import tempfile
 import tkinter as tk
 from tkinter import ttk
 from tkinter.scrolledtext import ScrolledText
 from tkinter import filedialog
-from tkinter.simpledialog import askinteger
+from tkinter.simpledialog import askinteger, askstring
 from tkinter import messagebox
 from tkinter import PhotoImage
 import webbrowser
@@ -33,6 +34,10 @@ from whitebox_tools import WhiteboxTools, to_camelcase
 
 wbt = WhiteboxTools()
 
+# The built-in colour ramps of the QuickView tool and the size (pixels) of its preview images
+QUICK_VIEW_PALETTES = ["grey", "viridis", "magma", "terrain", "spectral", "blue_white_red", "brown_green", "blues"]
+QUICK_VIEW_SIZE = 400
+
 
 class FileSelector(tk.Frame):
     def __init__(self, json_str, runner, master=None):
@@ -542,6 +547,7 @@ class OptionsInput(tk.Frame):
         frame2 = ttk.Frame(frame, padding='0.0i')
         opt = ttk.Combobox(frame2, width=40)
         opt.grid(row=0, column=0, sticky=tk.NSEW)
+        self.opt = opt
 
         self.value = None  # initialize in event of no default and no selection
         i = 1
@@ -741,6 +747,738 @@ class DataInput(tk.Frame):
         return 'break'
 
 
+#########################################################
+#                  Pipeline Builder                     #
+#########################################################
+# The pipeline builder chains tools into a workflow file that is run by the
+# Pipeline tool. Steps are saved in the same JSON format that the tool reads,
+# i.e. {"steps": [{"id": ..., "tool": ..., "args": {...}, "depends_on": [...]}]},
+# with the position of each step on the canvas stored under "position", a key
+# that the Pipeline tool ignores.
+
+def param_key(p):
+    ''' Returns the name of a parameter within the "args" of a workflow step.
+    '''
+    return p['flags'][len(p['flags']) - 1].lstrip('-')
+
+
+def param_file_type(p):
+    ''' Returns the kind of a file parameter (e.g. 'ExistingFile' or 'NewFile') 
+    and its file type, or (None, None) for other parameters.
+    '''
+    pt = p['parameter_type']
+    if isinstance(pt, dict):
+        for kind in ('ExistingFileOrFloat', 'ExistingFile', 'NewFile', 'FileList'):
+            if kind in pt:
+                return kind, pt[kind]
+    return None, None
+
+
+def file_kind(file_type):
+    ''' Splits a file type, e.g. {"Vector": "Point"}, into its kind and geometry.
+    '''
+    if isinstance(file_type, dict):
+        kind = next(iter(file_type))
+        return kind, file_type[kind]
+    return file_type, 'Any'
+
+
+def types_compatible(out_type, in_type):
+    ''' Returns whether the output of one tool may be read by an input of another.
+    '''
+    out_kind, out_geom = file_kind(out_type)
+    in_kind, in_geom = file_kind(in_type)
+    if out_kind == 'Any' or in_kind == 'Any':
+        return True
+    if in_kind == 'RasterAndVector':
+        return out_kind in ('Raster', 'Vector', 'RasterAndVector')
+    if out_kind == 'RasterAndVector':
+        return in_kind in ('Raster', 'Vector')
+    if out_kind != in_kind:
+        return False
+    if out_kind == 'Vector' and out_geom != 'Any' and in_geom != 'Any':
+        lines_or_polygons = ('Line', 'Polygon', 'LineOrPolygon')
+        if out_geom == 'LineOrPolygon' or in_geom == 'LineOrPolygon':
+            return out_geom in lines_or_polygons and in_geom in lines_or_polygons
+        return out_geom == in_geom
+    return True
+
+
+def split_file_list(value):
+    delimiter = ';' if ';' in value else ','
+    return [f.strip() for f in value.split(delimiter) if f.strip()]
+
+
+def describe_type(file_type):
+    kind, geom = file_kind(file_type)
+    if kind == 'Vector' and geom != 'Any':
+        return "{} vector".format(geom.lower())
+    return kind.lower()
+
+
+def step_outputs(step, params):
+    ''' Returns a dictionary of the output file names of a step and their parameters.
+    '''
+    ret = {}
+    for p in params:
+        kind, _ = param_file_type(p)
+        value = str(step['args'].get(param_key(p), '')).strip()
+        if kind == 'NewFile' and value:
+            ret[value] = p
+    return ret
+
+
+def step_inputs(step, params):
+    ''' Returns a list of the (file name, parameter) pairs of the inputs of a step.
+    '''
+    ret = []
+    for p in params:
+        kind, _ = param_file_type(p)
+        value = str(step['args'].get(param_key(p), '')).strip()
+        if not value or kind is None or kind == 'NewFile':
+            continue
+        if kind == 'FileList':
+            for f in split_file_list(value):
+                ret.append((f, p))
+        elif kind == 'ExistingFile' or not represents_float(value):
+            ret.append((value, p))
+    return ret
+
+
+def represents_float(s):
+    try:
+        float(s)
+        return True
+    except ValueError:
+        return False
+
+
+def step_links(steps, tool_params):
+    ''' Returns the (source step, target step, input parameter) links of the steps, 
+    where a step reads a file written by another step.
+    '''
+    outputs = {}
+    for s in steps:
+        for f in step_outputs(s, tool_params.get(s['tool']) or []):
+            outputs[f] = s
+    links = []
+    for t in steps:
+        for f, p in step_inputs(t, tool_params.get(t['tool']) or []):
+            s = outputs.get(f)
+            if s is not None and s is not t:
+                links.append((s, t, p))
+    return links
+
+
+def validate_pipeline(steps, tool_params):
+    ''' Checks the steps of a pipeline against the parameters of their tools, 
+    as returned by get_tool_parameters(), and returns a list of the problems found.
+    '''
+    errors = []
+    if len(steps) == 0:
+        return ["The pipeline contains no steps."]
+    ids = [s['id'] for s in steps]
+    for i, s in enumerate(steps):
+        if not s['id']:
+            errors.append("A step of the {} tool has no id.".format(s['tool']))
+        elif ids.count(s['id']) > 1 and ids.index(s['id']) == i:
+            errors.append("There is more than one step with the id '{}'.".format(s['id']))
+        for d in s.get('depends_on', []):
+            if d not in ids:
+                errors.append("Step '{}' depends on an unknown step '{}'.".format(s['id'], d))
+
+    outputs = {}
+    for s in steps:
+        params = tool_params.get(s['tool'])
+        if params is None:
+            errors.append("Step '{}' names an unrecognized tool '{}'.".format(s['id'], s['tool']))
+            continue
+        for p in params:
+            pt = p['parameter_type']
+            value = s['args'].get(param_key(p), '')
+            if pt == 'Boolean':
+                continue
+            value = str(value).strip()
+            if not value:
+                if not p['optional']:
+                    errors.append("Step '{}' ({}): the required parameter '{}' is not set.".format(s['id'], s['tool'], p['name']))
+                continue
+            if pt == 'Integer':
+                try:
+                    int(value)
+                except ValueError:
+                    errors.append("Step '{}' ({}): '{}' must be an integer.".format(s['id'], s['tool'], p['name']))
+            elif pt == 'Float':
+                if not represents_float(value):
+                    errors.append("Step '{}' ({}): '{}' must be a number.".format(s['id'], s['tool'], p['name']))
+            elif isinstance(pt, dict) and 'OptionList' in pt:
+                if value not in pt['OptionList']:
+                    errors.append("Step '{}' ({}): '{}' must be one of {}.".format(s['id'], s['tool'], p['name'], ', '.join(pt['OptionList'])))
+        for f, p in step_outputs(s, params).items():
+            _, file_type = param_file_type(p)
+            if f.startswith('memory:') and file_kind(file_type)[0] != 'Raster':
+                errors.append("Step '{}' writes {} to memory, but only raster outputs may be held in memory.".format(s['id'], f))
+            if f in outputs:
+                errors.append("Steps '{}' and '{}' both write {}.".format(outputs[f][0]['id'], s['id'], f))
+            else:
+                outputs[f] = (s, p)
+
+    for t in steps:
+        for f, p in step_inputs(t, tool_params.get(t['tool']) or []):
+            if f not in outputs:
+                if f.startswith('memory:'):
+                    errors.append("Step '{}' reads the in-memory dataset {}, which no step writes.".format(t['id'], f))
+                continue
+            s, out_param = outputs[f]
+            out_type = param_file_type(out_param)[1]
+            in_type = param_file_type(p)[1]
+            if not types_compatible(out_type, in_type):
+                errors.append("Step '{}' ({}): '{}' expects a {} file, but {} is a {} output of step '{}'.".format(
+                    t['id'], t['tool'], p['name'], describe_type(in_type), f, describe_type(out_type), s['id']))
+
+    # check for cycles, by repeatedly removing the steps whose predecessors have all been removed
+    links = step_links(steps, tool_params)
+    predecessors = {id(t): set(id(s) for s, tt, _ in links if tt is t) for t in steps}
+    for t in steps:
+        for d in t.get('depends_on', []):
+            predecessors[id(t)].update(id(s) for s in steps if s['id'] == d and s is not t)
+    done = set()
+    progress = True
+    while progress:
+        progress = False
+        for t in steps:
+            if id(t) not in done and predecessors[id(t)] <= done:
+                done.add(id(t))
+                progress = True
+    if len(done) < len(steps):
+        remaining = ["'{}'".format(t['id']) for t in steps if id(t) not in done]
+        errors.append("The pipeline contains a cycle among the steps {}.".format(', '.join(remaining)))
+    return errors
+
+
+def arg_key(flag, params):
+    ''' Returns the name of the parameter with a flag, e.g. 'input' for '-i'.
+    '''
+    key = flag.strip().lstrip('-')
+    for p in params:
+        if '-' + key in p['flags'] or '--' + key in p['flags']:
+            return param_key(p)
+    return key
+
+
+def step_args(workflow_args, params):
+    ''' Converts the "args" of a workflow step, either an object of parameter values or a 
+    list of command-line arguments, to a dictionary of parameter values.
+    '''
+    args = {}
+    if isinstance(workflow_args, list):
+        for a in workflow_args:
+            flag, sep, value = str(a).partition('=')
+            args[arg_key(flag, params)] = value.strip('\'"') if sep else True
+    elif isinstance(workflow_args, dict):
+        for k, v in workflow_args.items():
+            if isinstance(v, list):
+                v = ';'.join(str(x) for x in v)
+            if v is not None and v is not False:
+                args[arg_key(k, params)] = v
+    return args
+
+
+class PipelineBuilder(tk.Toplevel):
+    NODE_WIDTH = 170
+    NODE_HEIGHT = 44
+
+    def __init__(self, runner):
+        tk.Toplevel.__init__(self, runner)
+        self.runner = runner
+        self.title("Pipeline Builder")
+        self.steps = []
+        self.tool_params = {}
+        self.selected = None
+        self.file_name = None
+        self.drag = None
+        self.next_key = 0
+        self.create_widgets()
+
+    def create_widgets(self):
+        #Create the elements of the toolbar frame
+        toolbar = ttk.Frame(self, padding='0.05i')
+        self.tool_var = tk.StringVar(value=self.runner.tool_name)
+        tool_box = ttk.Combobox(toolbar, textvariable=self.tool_var, values=self.runner.tools_list, width=32)
+        add_button = ttk.Button(toolbar, text="Add Tool", command=self.add_tool)
+        remove_button = ttk.Button(toolbar, text="Remove Step", command=self.remove_step)
+        validate_button = ttk.Button(toolbar, text="Validate", command=self.validate)
+        open_button = ttk.Button(toolbar, text="Open...", command=self.open_pipeline)
+        save_button = ttk.Button(toolbar, text="Save...", command=self.save_pipeline)
+        run_button = ttk.Button(toolbar, text="Run", command=self.run_pipeline)
+        #Define layout of the frame
+        for i, w in enumerate([tool_box, add_button, remove_button, validate_button, open_button, save_button, run_button]):
+            w.grid(row=0, column=i, padx=2)
+        toolbar.grid(row=0, column=0, columnspan=2, sticky=tk.W)
+        #Create the canvas, on which each step is a node, with a port on its right side from 
+        #which its outputs are dragged to the inputs of other steps
+        self.canvas = tk.Canvas(self, width=760, height=480, background='white', highlightthickness=0)
+        self.canvas.grid(row=1, column=0, sticky=tk.NSEW)
+        self.canvas.bind("<ButtonPress-1>", self.on_press)
+        self.canvas.bind("<B1-Motion>", self.on_motion)
+        self.canvas.bind("<ButtonRelease-1>", self.on_release)
+        self.bind("<Delete>", lambda event: self.remove_step() if event.widget is self.canvas else None)
+        #Create the frame of the parameters of the selected step
+        self.param_frame = ttk.LabelFrame(self, text="Step Parameters", padding='0.1i')
+        self.param_frame.grid(row=1, column=1, sticky=tk.NSEW)
+        self.param_frame.columnconfigure(1, weight=1)
+        #Configure rows and columns
+        self.columnconfigure(0, weight=1)
+        self.rowconfigure(1, weight=1)
+        self.update_param_frame()
+
+    def get_params(self, tool):
+        if tool not in self.tool_params:
+            try:
+                self.tool_params[tool] = json.loads(wbt.tool_parameters(tool))['parameters']
+            except Exception:
+                self.tool_params[tool] = None
+        return self.tool_params[tool]
+
+    def new_step(self, tool, step_id=None, args=None, depends_on=None, position=None):
+        n = len(self.steps)
+        if step_id is None:
+            i = n + 1
+            while "step{}".format(i) in [s['id'] for s in self.steps]:
+                i += 1
+            step_id = "step{}".format(i)
+        if position is None:
+            position = [20 + (n % 4) * 190, 20 + (n // 4) * 80]
+        step = {'id': step_id, 'tool': tool, 'args': args or {}, 'depends_on': depends_on or [],
+                'position': position, 'key': "k{}".format(self.next_key)}
+        self.next_key += 1
+        self.steps.append(step)
+        return step
+
+    def add_tool(self):
+        tool = self.tool_var.get().strip()
+        params = self.get_params(tool)
+        if params is None:
+            messagebox.showinfo("Error", "Unrecognized tool: {}.".format(tool), parent=self)
+            return
+        args = {}
+        for p in params:
+            if p['default_value'] is not None and param_file_type(p)[0] is None:
+                args[param_key(p)] = (p['default_value'].lower() == 'true') if p['parameter_type'] == 'Boolean' else p['default_value']
+        self.selected = self.new_step(tool, args=args)
+        self.redraw()
+        self.update_param_frame()
+
+    def remove_step(self):
+        if self.selected is None:
+            return
+        for s in self.steps:
+            if self.selected['id'] in s['depends_on']:
+                s['depends_on'].remove(self.selected['id'])
+        self.steps.remove(self.selected)
+        self.selected = None
+        self.redraw()
+        self.update_param_frame()
+
+    def step_at(self, x, y):
+        for item in reversed(self.canvas.find_overlapping(x, y, x, y)):
+            for tag in self.canvas.gettags(item):
+                for s in self.steps:
+                    if s['key'] == tag:
+                        return s, 'port' in self.canvas.gettags(item)
+        return None, False
+
+    def redraw(self):
+        self.canvas.delete(tk.ALL)
+        w, h = self.NODE_WIDTH, self.NODE_HEIGHT
+        # links are drawn from the port of the step that writes a file to each step that reads it
+        for s, t, p in step_links(self.steps, self.tool_params):
+            self.draw_link(s, t, param_key(p), ())
+        for t in self.steps:
+            for s in self.steps:
+                if s['id'] in t['depends_on'] and s is not t:
+                    self.draw_link(s, t, '', (4, 3))
+        for s in self.steps:
+            x, y = s['position']
+            outline = '#1f6fb2' if s is self.selected else '#555555'
+            fill = '#e8f0fb' if self.get_params(s['tool']) is not None else '#fbe8e8'
+            self.canvas.create_rectangle(x, y, x + w, y + h, fill=fill, outline=outline, width=2, tags=(s['key'],))
+            self.canvas.create_text(x + 8, y + 13, text=s['id'], anchor=tk.W, font=('Helvetica', 10, 'bold'), tags=(s['key'],))
+            self.canvas.create_text(x + 8, y + 31, text=s['tool'], anchor=tk.W, tags=(s['key'],))
+            self.canvas.create_oval(x + w - 6, y + h / 2 - 6, x + w + 6, y + h / 2 + 6, fill='#1f6fb2', outline='', tags=(s['key'], 'port'))
+
+    def draw_link(self, s, t, label, dash):
+        x1 = s['position'][0] + self.NODE_WIDTH + 6
+        y1 = s['position'][1] + self.NODE_HEIGHT / 2
+        x2 = t['position'][0]
+        y2 = t['position'][1] + self.NODE_HEIGHT / 2
+        self.canvas.create_line(x1, y1, x2, y2, arrow=tk.LAST, fill='#555555', dash=dash, width=2)
+        if label:
+            self.canvas.create_text((x1 + x2) / 2, (y1 + y2) / 2 - 8, text=label, fill='#1f6fb2')
+
+    def on_press(self, event):
+        self.canvas.focus_set()
+        step, is_port = self.step_at(event.x, event.y)
+        if step is not self.selected:
+            self.selected = step
+            self.update_param_frame()
+        if step is None:
+            self.drag = None
+        elif is_port:
+            self.drag = ('link', step, None)
+        else:
+            self.drag = ('move', step, (event.x - step['position'][0], event.y - step['position'][1]))
+        self.redraw()
+        if self.drag is not None and self.drag[0] == 'link':
+            x, y = step['position'][0] + self.NODE_WIDTH, step['position'][1] + self.NODE_HEIGHT / 2
+            self.drag = ('link', step, self.canvas.create_line(x, y, event.x, event.y, arrow=tk.LAST, dash=(4, 3)))
+
+    def on_motion(self, event):
+        if self.drag is None:
+            return
+        mode, step, data = self.drag
+        if mode == 'move':
+            step['position'] = [max(0, event.x - data[0]), max(0, event.y - data[1])]
+            self.redraw()
+        else:
+            x1, y1, _, _ = self.canvas.coords(data)
+            self.canvas.coords(data, x1, y1, event.x, event.y)
+
+    def on_release(self, event):
+        if self.drag is None:
+            return
+        mode, step, data = self.drag
+        self.drag = None
+        if mode == 'link':
+            self.canvas.delete(data)
+            target, _ = self.step_at(event.x, event.y)
+            if target is not None and target is not step:
+                self.connect(step, target)
+
+    def connect(self, source, target):
+        ''' Maps an output of the source step to an input of the target step, 
+        offering only the pairs of parameters with compatible file types.
+        '''
+        out_params = [p for p in self.get_params(source['tool']) or [] if param_file_type(p)[0] == 'NewFile']
+        in_params = [p for p in self.get_params(target['tool']) or [] if param_file_type(p)[0] not in (None, 'NewFile')]
+        pairs = [(o, i) for o in out_params for i in in_params
+                 if types_compatible(param_file_type(o)[1], param_file_type(i)[1])]
+        if len(pairs) == 0:
+            messagebox.showinfo("Error", "No output of {} can be read by an input of {}.".format(
+                source['tool'], target['tool']), parent=self)
+            return
+        if len(pairs) == 1:
+            self.link_params(source, target, pairs[0])
+            return
+        # let the user choose among the compatible pairs
+        dialog = tk.Toplevel(self)
+        dialog.title("Connect {} to {}".format(source['id'], target['id']))
+        labels = ["{}  →  {}".format(o['name'], i['name']) for o, i in pairs]
+        choice = tk.StringVar(value=labels[0])
+        ttk.Label(dialog, text="Output → Input:").grid(row=0, column=0, padx=5, pady=5)
+        ttk.Combobox(dialog, textvariable=choice, values=labels, state='readonly', width=60).grid(row=0, column=1, padx=5, pady=5)
+        def ok():
+            self.link_params(source, target, pairs[labels.index(choice.get())])
+            dialog.destroy()
+        ttk.Button(dialog, text="OK", command=ok).grid(row=1, column=1, sticky=tk.E, padx=5, pady=5)
+        dialog.transient(self)
+        dialog.grab_set()
+
+    def link_params(self, source, target, pair):
+        out_param, in_param = pair
+        out_key, in_key = param_key(out_param), param_key(in_param)
+        value = str(source['args'].get(out_key, '')).strip()
+        if not value:
+            # intermediate rasters are passed in memory; other outputs are written to files
+            kind, _ = file_kind(param_file_type(out_param)[1])
+            if kind == 'Raster':
+                value = "memory:{}_{}".format(source['id'], out_key)
+            else:
+                ext = {'Vector': '.shp', 'Lidar': '.las', 'Html': '.html', 'Csv': '.csv', 'Text': '.txt'}.get(kind, '')
+                value = "{}_{}{}".format(source['id'], out_key, ext)
+            source['args'][out_key] = value
+        if param_file_type(in_param)[0] == 'FileList':
+            files = split_file_list(str(target['args'].get(in_key, '')))
+            if value not in files:
+                files.append(value)
+            target['args'][in_key] = ';'.join(files)
+        else:
+            target['args'][in_key] = value
+        self.redraw()
+        self.update_param_frame()
+
+    def update_param_frame(self):
+        for widget in self.param_frame.winfo_children():
+            widget.destroy()
+        step = self.selected
+        if step is None:
+            ttk.Label(self.param_frame, text="Select a step, or drag from the port\nof a step to another step to connect them.").grid(row=0, column=0)
+            return
+        ttk.Label(self.param_frame, text="Step id").grid(row=0, column=0, sticky=tk.W)
+        id_entry = ttk.Entry(self.param_frame, width=30)
+        id_entry.insert(0, step['id'])
+        id_entry.bind('<Return>', lambda e: self.rename_step(step, id_entry))
+        id_entry.bind('<FocusOut>', lambda e: self.rename_step(step, id_entry))
+        id_entry.grid(row=0, column=1, columnspan=2, sticky=tk.EW)
+        ttk.Label(self.param_frame, text="Depends on").grid(row=1, column=0, sticky=tk.W)
+        depends_var = tk.StringVar(value=', '.join(step['depends_on']))
+        def set_depends(*args):
+            step['depends_on'] = [d.strip() for d in depends_var.get().split(',') if d.strip()]
+            self.redraw()
+        depends_var.trace_add('write', set_depends)
+        ttk.Entry(self.param_frame, textvariable=depends_var, width=30).grid(row=1, column=1, columnspan=2, sticky=tk.EW)
+        row = 2
+        for p in self.get_params(step['tool']) or []:
+            key = param_key(p)
+            pt = p['parameter_type']
+            label = p['name'] if p['optional'] else p['name'] + " *"
+            ttk.Label(self.param_frame, text=label).grid(row=row, column=0, sticky=tk.W)
+            if pt == 'Boolean':
+                var = tk.BooleanVar(value=bool(step['args'].get(key, False)))
+                ttk.Checkbutton(self.param_frame, variable=var).grid(row=row, column=1, sticky=tk.W)
+            else:
+                var = tk.StringVar(value=str(step['args'].get(key, '')))
+                if isinstance(pt, dict) and 'OptionList' in pt:
+                    ttk.Combobox(self.param_frame, textvariable=var, values=pt['OptionList'], state='readonly', width=28).grid(row=row, column=1, sticky=tk.EW)
+                else:
+                    ttk.Entry(self.param_frame, textvariable=var, width=30).grid(row=row, column=1, sticky=tk.EW)
+                kind = param_file_type(p)[0]
+                if kind is not None:
+                    ttk.Button(self.param_frame, text="...", width=3, command=lambda v=var, k=kind: self.select_file(v, k)).grid(row=row, column=2)
+            var.trace_add('write', lambda *args, v=var, k=key: self.set_arg(step, k, v))
+            row += 1
+
+    def set_arg(self, step, key, var):
+        value = var.get()
+        if value == '' or value is False:
+            step['args'].pop(key, None)
+        else:
+            step['args'][key] = value
+        self.redraw()
+
+    def rename_step(self, step, entry):
+        if not entry.winfo_exists():
+            return
+        new_id = entry.get().strip()
+        if new_id == step['id']:
+            return
+        if not new_id or new_id in [s['id'] for s in self.steps]:
+            messagebox.showinfo("Error", "Step ids must be unique and non-empty.", parent=self)
+            entry.delete(0, tk.END)
+            entry.insert(0, step['id'])
+            return
+        for s in self.steps:
+            s['depends_on'] = [new_id if d == step['id'] else d for d in s['depends_on']]
+        step['id'] = new_id
+        self.redraw()
+
+    def select_file(self, var, kind):
+        if kind == 'NewFile':
+            name = filedialog.asksaveasfilename(initialdir=self.runner.working_dir, parent=self)
+        else:
+            name = filedialog.askopenfilename(initialdir=self.runner.working_dir, parent=self)
+        if not name:
+            return
+        if path.dirname(name) == path.normpath(self.runner.working_dir):
+            name = path.basename(name)
+        if kind == 'FileList' and var.get().strip():
+            var.set(var.get().strip() + ';' + name)
+        else:
+            var.set(name)
+
+    def validate(self):
+        for s in self.steps:
+            self.get_params(s['tool'])
+        errors = validate_pipeline(self.steps, self.tool_params)
+        if errors:
+            self.runner.print_line_to_output("The pipeline is not valid:")
+            for e in errors:
+                self.runner.print_line_to_output("  " + e)
+        else:
+            self.runner.print_line_to_output("The pipeline is valid ({} steps).".format(len(self.steps)))
+        return len(errors) == 0
+
+    def to_json(self):
+        steps = []
+        for s in self.steps:
+            step = {'id': s['id'], 'tool': s['tool'], 'args': dict(s['args'])}
+            if s['depends_on']:
+                step['depends_on'] = list(s['depends_on'])
+            step['position'] = [int(s['position'][0]), int(s['position'][1])]
+            steps.append(step)
+        return {'steps': steps}
+
+    def save_pipeline(self):
+        name = filedialog.asksaveasfilename(initialdir=self.runner.working_dir, defaultextension='.json',
+                                            filetypes=[('Workflow files', '*.json')], parent=self)
+        if not name:
+            return False
+        try:
+            with open(name, 'w') as f:
+                json.dump(self.to_json(), f, indent=2)
+            self.file_name = name
+            self.title("Pipeline Builder - {}".format(path.basename(name)))
+            return True
+        except OSError as e:
+            messagebox.showinfo("Error", "Could not save the pipeline: {}".format(e), parent=self)
+            return False
+
+    def open_pipeline(self):
+        name = filedialog.askopenfilename(initialdir=self.runner.working_dir,
+                                          filetypes=[('Workflow files', '*.json')], parent=self)
+        if not name:
+            return
+        try:
+            with open(name) as f:
+                workflow = json.load(f)
+        except (OSError, ValueError) as e:
+            messagebox.showinfo("Error", "Could not read the pipeline: {}".format(e), parent=self)
+            return
+        self.steps = []
+        self.selected = None
+        for i, s in enumerate(workflow.get('steps', [])):
+            tool = str(s.get('tool', '')).strip()
+            args = step_args(s.get('args'), self.get_params(tool) or [])
+            depends_on = s.get('depends_on', [])
+            if isinstance(depends_on, str):
+                depends_on = [depends_on]
+            position = s.get('position')
+            if not (isinstance(position, list) and len(position) == 2):
+                position = None
+            self.new_step(tool, s.get('id', "step{}".format(i + 1)), args, depends_on, position)
+        self.file_name = name
+        self.title("Pipeline Builder - {}".format(path.basename(name)))
+        self.redraw()
+        self.update_param_frame()
+        self.validate()
+
+    def run_pipeline(self):
+        if not self.validate():
+            return
+        # the pipeline is always saved before it is run, so that the run is reproducible from the file
+        if self.file_name is None:
+            if not self.save_pipeline():
+                return
+        else:
+            with open(self.file_name, 'w') as f:
+                json.dump(self.to_json(), f, indent=2)
+        wbt.set_working_dir(self.runner.working_dir)
+        self.runner.print_line_to_output("")
+        if wbt.pipeline(self.file_name, callback=self.runner.custom_callback) == 1:
+            print("Error running the pipeline {}".format(self.file_name))
+        else:
+            self.runner.progress_var.set(0)
+            self.runner.progress_label['text'] = "Progress:"
+            self.runner.progress.update_idletasks()
+
+
+class QuickViewPanel(ttk.LabelFrame):
+    """ A panel beside the tool parameters that previews the input and output datasets of
+        the last tool run, rendered by the QuickView tool from raster overviews, such that
+        each step of a workflow can be checked without opening the data in a GIS.
+    """
+    def __init__(self, runner):
+        ttk.LabelFrame.__init__(self, runner, text="Quick View", padding='0.1i')
+        self.runner = runner
+        self.rasters = []
+        self.vectors = []
+        self.image = None
+        self.temp_dir = tempfile.TemporaryDirectory()
+        self.create_widgets()
+
+    def create_widgets(self):
+        #Create the elements of the panel
+        self.dataset_var = tk.StringVar()
+        self.dataset_box = ttk.Combobox(self, textvariable=self.dataset_var, state='readonly', width=40)
+        self.dataset_box.bind("<<ComboboxSelected>>", lambda event: self.render())
+        self.palette_var = tk.StringVar(value="viridis")
+        palette_box = ttk.Combobox(self, textvariable=self.palette_var, values=QUICK_VIEW_PALETTES, state='readonly', width=14)
+        palette_box.bind("<<ComboboxSelected>>", lambda event: self.render())
+        self.hillshade_var = tk.BooleanVar(value=False)
+        hillshade_button = ttk.Checkbutton(self, text="Hillshade", variable=self.hillshade_var, command=self.render)
+        self.overlay_var = tk.BooleanVar(value=True)
+        overlay_button = ttk.Checkbutton(self, text="Overlay vectors", variable=self.overlay_var, command=self.render)
+        self.image_label = ttk.Label(self, anchor=tk.CENTER)
+        self.status_label = ttk.Label(self, text="", justify=tk.LEFT)
+        close_button = ttk.Button(self, text="Close", command=self.hide)
+        #Define layout of the panel
+        ttk.Label(self, text="Dataset:").grid(row=0, column=0, sticky=tk.W)
+        self.dataset_box.grid(row=0, column=1, columnspan=3, sticky=tk.EW, pady=2)
+        ttk.Label(self, text="Palette:").grid(row=1, column=0, sticky=tk.W)
+        palette_box.grid(row=1, column=1, sticky=tk.W, pady=2)
+        hillshade_button.grid(row=1, column=2, sticky=tk.W, padx=5)
+        overlay_button.grid(row=1, column=3, sticky=tk.W)
+        self.image_label.grid(row=2, column=0, columnspan=4, sticky=tk.NSEW, pady=5)
+        self.status_label.grid(row=3, column=0, columnspan=3, sticky=tk.W)
+        close_button.grid(row=3, column=3, sticky=tk.E)
+        #Configure rows and columns
+        self.columnconfigure(3, weight=1)
+        self.rowconfigure(2, weight=1)
+
+    def show(self):
+        self.grid(row=0, column=2, sticky=tk.NSEW)
+
+    def hide(self):
+        self.grid_remove()
+
+    def toggle(self):
+        if self.winfo_ismapped():
+            self.hide()
+        else:
+            self.show()
+            self.render()
+
+    def set_datasets(self, rasters, vectors):
+        """ Displays a set of datasets, given as lists of file names, showing the last raster,
+            which is usually the output of the tool that created them.
+        """
+        self.rasters = rasters
+        self.vectors = vectors
+        self.dataset_box['values'] = [path.basename(f) for f in rasters]
+        self.dataset_var.set(path.basename(rasters[-1]) if rasters else "")
+        self.show()
+        self.render()
+
+    def render(self):
+        if not self.rasters and not self.vectors:
+            self.status_label['text'] = "Run a tool to preview its datasets."
+            return
+        args = []
+        if self.rasters:
+            names = [path.basename(f) for f in self.rasters]
+            name = self.dataset_var.get()
+            raster = self.rasters[names.index(name)] if name in names else self.rasters[-1]
+            args.append("--input='{}'".format(raster))
+            args.append("--palette={}".format(self.palette_var.get()))
+            if self.hillshade_var.get():
+                args.append("--hillshade")
+        if self.vectors and (self.overlay_var.get() or not self.rasters):
+            args.append("--vectors='{}'".format(';'.join(self.vectors)))
+        if len(args) == 0:
+            return
+        output = path.join(self.temp_dir.name, "quick_view.png")
+        args.append("--output='{}'".format(output))
+        args.append("--max_size={}".format(QUICK_VIEW_SIZE))
+        messages = []
+        self.status_label['text'] = "Rendering..."
+        self.update_idletasks()
+        if wbt.run_tool("QuickView", args, messages.append) == 1 or not path.exists(output):
+            self.image = None
+            self.image_label['image'] = ''
+            self.status_label['text'] = "The datasets could not be previewed."
+            for m in messages:
+                if "error" in m.lower():
+                    self.runner.print_line_to_output(m)
+            return
+        try:
+            self.image = tk.PhotoImage(file=output)
+            self.image_label['image'] = self.image
+            self.status_label['text'] = ""
+        except tk.TclError:
+            # PNG images require Tk 8.6 or later
+            self.status_label['text'] = "The preview image could not be displayed."
+
+
 class WbRunner(tk.Frame):
     def __init__(self, tool_name=None, master=None):
         if platform.system() == 'Windows':
@@ -898,10 +1636,20 @@ class WbRunner(tk.Frame):
         self.run_button = ttk.Button(buttons_frame, text="Run", width=8, command=self.run_tool)
         self.quit_button = ttk.Button(buttons_frame, text="Cancel", width=8, command=self.cancel_operation)
         self.help_button = ttk.Button(buttons_frame, text="Help", width=8, command=self.tool_help_button)
+        presets_label = ttk.Label(buttons_frame, text="Preset:")
+        self.preset_var = tk.StringVar()
+        self.preset_box = ttk.Combobox(buttons_frame, textvariable=self.preset_var, state='readonly', width=18)
+        self.preset_box.bind("<<ComboboxSelected>>", self.select_preset)
+        self.save_preset_button = ttk.Button(buttons_frame, text="Save Preset...", command=self.save_preset)
+        self.delete_preset_button = ttk.Button(buttons_frame, text="Delete Preset", command=self.delete_preset)
         #Define layout of the frame
-        self.run_button.grid(row=0, column=0)
-        self.quit_button.grid(row=0, column=1)
-        self.help_button.grid(row = 0, column = 2)
+        presets_label.grid(row=0, column=0, sticky=tk.W)
+        self.preset_box.grid(row=0, column=1, sticky=tk.W)
+        self.save_preset_button.grid(row=0, column=2)
+        self.delete_preset_button.grid(row=0, column=3)
+        self.run_button.grid(row=1, column=1, sticky=tk.E)
+        self.quit_button.grid(row=1, column=2)
+        self.help_button.grid(row = 1, column = 3)
         buttons_frame.grid(row=2, column=0, columnspan = 2, sticky=tk.E)
         #########################################################
         #                  Output Frame                      #
@@ -941,6 +1689,12 @@ class WbRunner(tk.Frame):
         self.progress.grid(row=0, column=1, sticky=tk.E)
         progress_frame.grid(row=4, column=0, columnspan = 2, sticky=tk.SE)
         #########################################################
+        #                  Quick View Panel                     #
+        #########################################################
+        #The panel is shown to the right of the tool parameters once a tool has run
+        self.quick_view = QuickViewPanel(self)
+        self.preview_var = tk.BooleanVar(value=True)
+        #########################################################
         #                  Tool Selection                       #
         #########################################################        
         # Select the appropriate tool, if specified, otherwise the first tool
@@ -981,6 +1735,15 @@ class WbRunner(tk.Frame):
         editmenu.add_command(label="Paste", command=lambda: self.focus_get().event_generate("<<Paste>>"))
         menubar.add_cascade(label="Edit ", menu=editmenu)
 
+        viewmenu = tk.Menu(menubar, tearoff=0)
+        viewmenu.add_command(label="Quick View Panel", command=self.quick_view.toggle)
+        viewmenu.add_checkbutton(label="Preview Datasets After Runs", variable=self.preview_var)
+        menubar.add_cascade(label="View ", menu=viewmenu)
+
+        pipelinemenu = tk.Menu(menubar, tearoff=0)
+        pipelinemenu.add_command(label="Pipeline Builder", command=self.pipeline_builder)
+        menubar.add_cascade(label="Pipeline ", menu=pipelinemenu)
+
         helpmenu = tk.Menu(menubar, tearoff=0)
         helpmenu.add_command(label="About", command=self.help)
         helpmenu.add_command(label="License", command=self.license)
@@ -1115,8 +1878,145 @@ class WbRunner(tk.Frame):
                 messagebox.showinfo(
                     "Error", "Unsupported parameter type: {}.".format(pt))
         self.update_args_box()
+        self.load_presets()
         self.out_text.see("%d.%d" % (1, 0))
 
+    def load_presets(self):
+        ''' Lists the presets of the current tool, read from the user's and the shared presets
+            files, and fills in the parameters of the tool's 'default' preset, if it has one.
+        '''
+        self.presets = {}
+        self.presets_file = ""
+        try:
+            j = json.loads(wbt.list_presets(self.tool_name))
+            self.presets_file = j['user_file']
+            for tool, presets in j['presets'].items():
+                if tool.replace('_', '').lower() == self.tool_name.lower():
+                    self.presets = presets
+        except (ValueError, KeyError, TypeError):
+            self.print_line_to_output("The tool presets could not be read.")
+        self.preset_box['values'] = sorted(self.presets.keys())
+        self.preset_var.set("")
+        if 'default' in self.presets:
+            self.preset_var.set('default')
+            self.apply_preset_values(self.presets['default'])
+
+    def select_preset(self, event):
+        name = self.preset_var.get()
+        if name in self.presets:
+            self.apply_preset_values(self.presets[name])
+
+    def apply_preset_values(self, values):
+        ''' Fills in the tool parameters with the values of a preset, which are keyed by any of
+            the parameters' flags, with or without the leading dashes.
+        '''
+        values = dict((k.lstrip('-').lower(), v) for k, v in values.items())
+        for widget in self.arg_scroll_frame.winfo_children():
+            key = widget.flag.lstrip('-').lower()
+            if key not in values:
+                continue
+            v = values[key]
+            if isinstance(widget, BooleanInput):
+                widget.value.set(1 if v is True or str(v).lower() == 'true' else 0)
+            elif isinstance(widget, OptionsInput):
+                widget.opt.set(str(v))
+                widget.value = str(v)
+            elif isinstance(widget, MultifileSelector):
+                widget.opt.delete(0, tk.END)
+                for f in (v if isinstance(v, list) else str(v).split(';')):
+                    if f.strip():
+                        widget.opt.insert(tk.END, f.strip())
+            elif isinstance(widget, FileOrFloat):
+                if isinstance(v, (int, float)) or represents_float(str(v)):
+                    widget.value.set("")
+                    widget.value2.set(str(v))
+                else:
+                    widget.value.set(str(v))
+                    widget.value2.set("")
+            elif v is not None:
+                widget.value.set(str(v))
+
+    def preset_values(self):
+        ''' Returns the values of the tool parameters that are saved in a preset. The names of
+            files are specific to a dataset and are left out, other than numeric values given in
+            place of a file.
+        '''
+        values = {}
+        for widget in self.arg_scroll_frame.winfo_children():
+            key = widget.flag.lstrip('-')
+            if isinstance(widget, BooleanInput):
+                values[key] = widget.value.get() == 1
+            elif isinstance(widget, OptionsInput):
+                if widget.value:
+                    values[key] = widget.value
+            elif isinstance(widget, FileOrFloat):
+                if widget.value2.get().strip():
+                    values[key] = widget.value2.get().strip()
+            elif isinstance(widget, DataInput):
+                if widget.value.get().strip():
+                    values[key] = widget.value.get().strip()
+        return values
+
+    def write_presets(self, update):
+        ''' Updates the current tool's presets in the user's presets file.
+        '''
+        if not self.presets_file:
+            messagebox.showinfo("Error", "The user presets file could not be located.")
+            return False
+        presets = {}
+        if path.exists(self.presets_file):
+            try:
+                with open(self.presets_file, 'r') as f:
+                    presets = json.load(f)
+            except (OSError, ValueError) as err:
+                messagebox.showinfo("Error", "The presets file {} could not be read: {}".format(self.presets_file, err))
+                return False
+        tool = next((t for t in presets if t.replace('_', '').lower() == self.tool_name.lower()), self.tool_name)
+        tool_presets = presets.get(tool, {})
+        update(tool_presets)
+        if tool_presets:
+            presets[tool] = tool_presets
+        elif tool in presets:
+            del presets[tool]
+        try:
+            os.makedirs(path.dirname(self.presets_file), exist_ok=True)
+            with open(self.presets_file, 'w') as f:
+                json.dump(presets, f, indent=2, sort_keys=True)
+        except OSError as err:
+            messagebox.showinfo("Error", "The presets file {} could not be written: {}".format(self.presets_file, err))
+            return False
+        return True
+
+    def save_preset(self):
+        name = askstring("Save Preset", "Preset name (presets named 'default' are applied automatically):",
+                         initialvalue=self.preset_var.get(), parent=self)
+        if not name or not name.strip():
+            return
+        name = name.strip()
+        values = self.preset_values()
+        if self.write_presets(lambda p: p.update({name: values})):
+            self.load_presets()
+            self.preset_var.set(name)
+            self.print_line_to_output("Saved the preset '{}' to {}".format(name, self.presets_file))
+
+    def delete_preset(self):
+        name = self.preset_var.get()
+        if not name:
+            return
+        if not messagebox.askyesno("Delete Preset", "Delete the preset '{}'?".format(name)):
+            return
+        deleted = []
+        def update(tool_presets):
+            if name in tool_presets:
+                del tool_presets[name]
+                deleted.append(name)
+        if self.write_presets(update):
+            if not deleted:
+                messagebox.showinfo("Error", "The preset '{}' is a shared preset, which can only be deleted from the shared presets file.".format(name))
+                return
+            self.load_presets()
+            self.print_line_to_output("Deleted the preset '{}'".format(name))
+
     def update_toolbox_icon(self, event):
         curItem = self.tool_tree.focus()
         dict = self.tool_tree.item(curItem)    #retrieve the toolbox name
@@ -1225,6 +2125,9 @@ class WbRunner(tk.Frame):
     #########################################################
     #               Functions (original)                    #
     #########################################################
+    def pipeline_builder(self):
+        PipelineBuilder(self)
+
     def help(self):
         self.print_to_output(wbt.version())
 
@@ -1274,6 +2177,8 @@ class WbRunner(tk.Frame):
                 messagebox.showinfo(
                     "Error", "Non-optional tool parameter not specified.")
                 return
+        # the parameters already show the values of any selected preset
+        args.append("--preset=none")
 
         self.print_line_to_output("")
         # self.print_line_to_output("Tool arguments:{}".format(args))
@@ -1287,6 +2192,33 @@ class WbRunner(tk.Frame):
             self.progress_var.set(0)
             self.progress_label['text'] = "Progress:"
             self.progress.update_idletasks()
+            if self.preview_var.get() and self.tool_name != "QuickView":
+                self.preview_datasets()
+
+    def preview_datasets(self):
+        ''' Shows the raster and vector inputs and outputs of the current tool in the quick view panel.
+        '''
+        rasters = []
+        vectors = []
+        for widget in self.arg_scroll_frame.winfo_children():
+            if not isinstance(widget, (FileSelector, MultifileSelector)) or widget.parameter_type == "Directory":
+                continue
+            if isinstance(widget, FileSelector) and not widget.value.get():
+                continue
+            v = widget.get_value()
+            if not v:
+                continue
+            file_type = str(widget.file_type)
+            for f in v[v.index("=") + 1:].strip("'").split(';'):
+                ext = os.path.splitext(f)[-1].lower()
+                if not path.exists(f) or 'Lidar' in file_type:
+                    continue
+                if ext == '.shp' or ('Vector' in file_type and 'RasterAndVector' not in file_type):
+                    vectors.append(f)
+                elif 'Raster' in file_type:
+                    rasters.append(f)
+        if rasters or vectors:
+            self.quick_view.set_datasets(rasters, vectors)
 
     def print_to_output(self, value):
         self.out_text.insert(tk.END, value)
@@ -0,0 +1,177 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use crate::structures::Point2D;
+
+/// Calculates the weighted mean center of a set of points. All weights should be
+/// non-negative and the weights must not all be zero.
+pub fn weighted_mean_center(points: &[Point2D], weights: &[f64]) -> Point2D {
+    let mut sum_w = 0f64;
+    let mut sum_x = 0f64;
+    let mut sum_y = 0f64;
+    for i in 0..points.len() {
+        sum_w += weights[i];
+        sum_x += weights[i] * points[i].x;
+        sum_y += weights[i] * points[i].y;
+    }
+    Point2D::new(sum_x / sum_w, sum_y / sum_w)
+}
+
+/// Calculates the weighted median center of a set of points, i.e. the location that
+/// minimizes the sum of the weighted Euclidean distances to the points, using
+/// Weiszfeld's iterative algorithm. Iteration stops when the location moves less than
+/// `tolerance` or after `max_iterations`. Returns the median center and the number of
+/// iterations that were performed.
+pub fn median_center(
+    points: &[Point2D],
+    weights: &[f64],
+    tolerance: f64,
+    max_iterations: usize,
+) -> (Point2D, usize) {
+    let mut center = weighted_mean_center(points, weights);
+    let mut iteration = 0;
+    while iteration < max_iterations {
+        iteration += 1;
+        let mut sum_x = 0f64;
+        let mut sum_y = 0f64;
+        let mut sum_w = 0f64;
+        for i in 0..points.len() {
+            let d = center.distance(&points[i]);
+            if d > 0f64 {
+                sum_x += weights[i] * points[i].x / d;
+                sum_y += weights[i] * points[i].y / d;
+                sum_w += weights[i] / d;
+            }
+        }
+        if sum_w == 0f64 {
+            break;
+        }
+        let next = Point2D::new(sum_x / sum_w, sum_y / sum_w);
+        let movement = next.distance(&center);
+        center = next;
+        if movement < tolerance {
+            break;
+        }
+    }
+    (center, iteration)
+}
+
+/// Calculates the weighted standard distance of a set of points about a center point,
+/// i.e. the square root of the weighted mean squared distance from the center.
+pub fn standard_distance(points: &[Point2D], weights: &[f64], center: &Point2D) -> f64 {
+    let mut sum_w = 0f64;
+    let mut sum_d2 = 0f64;
+    for i in 0..points.len() {
+        sum_w += weights[i];
+        sum_d2 += weights[i] * center.distance_squared(&points[i]);
+    }
+    (sum_d2 / sum_w).sqrt()
+}
+
+/// Calculates the weighted standard deviational ellipse of a set of points about a
+/// center point. The ellipse axes are the principal axes of the weighted covariance
+/// matrix of the point coordinates. Returns the standard deviations along the major and
+/// minor axes and the rotation of the major axis, in radians clockwise from north
+/// (the positive y-axis), within the range [0, pi).
+pub fn standard_deviational_ellipse(
+    points: &[Point2D],
+    weights: &[f64],
+    center: &Point2D,
+) -> (f64, f64, f64) {
+    let mut sum_w = 0f64;
+    let mut sxx = 0f64;
+    let mut syy = 0f64;
+    let mut sxy = 0f64;
+    for i in 0..points.len() {
+        let dx = points[i].x - center.x;
+        let dy = points[i].y - center.y;
+        sum_w += weights[i];
+        sxx += weights[i] * dx * dx;
+        syy += weights[i] * dy * dy;
+        sxy += weights[i] * dx * dy;
+    }
+    sxx /= sum_w;
+    syy /= sum_w;
+    sxy /= sum_w;
+
+    // eigenvalues of the covariance matrix
+    let mean = (sxx + syy) / 2f64;
+    let diff = ((sxx - syy) * (sxx - syy) / 4f64 + sxy * sxy).sqrt();
+    let major = (mean + diff).max(0f64).sqrt();
+    let minor = (mean - diff).max(0f64).sqrt();
+
+    // angle of the major axis, counter-clockwise from the x-axis
+    let theta = 0.5 * (2f64 * sxy).atan2(sxx - syy);
+    // convert to clockwise from north
+    let mut rotation = std::f64::consts::FRAC_PI_2 - theta;
+    while rotation < 0f64 {
+        rotation += std::f64::consts::PI;
+    }
+    while rotation >= std::f64::consts::PI {
+        rotation -= std::f64::consts::PI;
+    }
+    (major, minor, rotation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::structures::Point2D;
+
+    #[test]
+    fn test_weighted_mean_center() {
+        let points = [Point2D::new(0.0, 0.0), Point2D::new(4.0, 0.0)];
+        let c = weighted_mean_center(&points, &[1.0, 1.0]);
+        assert!(c.nearly_equals(&Point2D::new(2.0, 0.0)));
+        let c = weighted_mean_center(&points, &[1.0, 3.0]);
+        assert!(c.nearly_equals(&Point2D::new(3.0, 0.0)));
+    }
+
+    #[test]
+    fn test_median_center() {
+        // the median center is resistant to the outlying point
+        let points = [
+            Point2D::new(0.0, 0.0),
+            Point2D::new(1.0, 0.0),
+            Point2D::new(0.0, 1.0),
+            Point2D::new(1.0, 1.0),
+            Point2D::new(100.0, 100.0),
+        ];
+        let (c, _) = median_center(&points, &[1.0; 5], 1e-9, 1000);
+        assert!(c.x < 2.0 && c.y < 2.0);
+        assert!(c.x > 0.5 && c.y > 0.5);
+    }
+
+    #[test]
+    fn test_standard_distance() {
+        let points = [
+            Point2D::new(1.0, 0.0),
+            Point2D::new(-1.0, 0.0),
+            Point2D::new(0.0, 1.0),
+            Point2D::new(0.0, -1.0),
+        ];
+        let sd = standard_distance(&points, &[1.0; 4], &Point2D::new(0.0, 0.0));
+        assert!((sd - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_standard_deviational_ellipse() {
+        // points along a north-east trending line
+        let points = [
+            Point2D::new(-2.0, -2.0),
+            Point2D::new(-1.0, -1.0),
+            Point2D::new(1.0, 1.0),
+            Point2D::new(2.0, 2.0),
+        ];
+        let (major, minor, rotation) =
+            standard_deviational_ellipse(&points, &[1.0; 4], &Point2D::new(0.0, 0.0));
+        assert!((rotation - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+        assert!(minor.abs() < 1e-9);
+        assert!((major - 5f64.sqrt()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,148 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+/// Calculates the global Moran's I statistic for a set of observations. The neighbours
+/// of each observation are given as lists of (index, weight) pairs, excluding the
+/// observation itself.
+pub fn global_morans_i(values: &[f64], neighbours: &[Vec<(usize, f64)>]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let total_deviation = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>();
+    let mut s0 = 0f64;
+    let mut numerator = 0f64;
+    for i in 0..values.len() {
+        for &(j, w) in &neighbours[i] {
+            s0 += w;
+            numerator += w * (values[i] - mean) * (values[j] - mean);
+        }
+    }
+    if s0 == 0f64 || total_deviation == 0f64 {
+        return 0f64;
+    }
+    n / s0 * numerator / total_deviation
+}
+
+/// Calculates Geary's C statistic for a set of observations. The neighbours of each
+/// observation are given as lists of (index, weight) pairs, excluding the observation
+/// itself. Values of C less than one indicate positive spatial autocorrelation.
+pub fn gearys_c(values: &[f64], neighbours: &[Vec<(usize, f64)>]) -> f64 {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let total_deviation = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>();
+    let mut s0 = 0f64;
+    let mut numerator = 0f64;
+    for i in 0..values.len() {
+        for &(j, w) in &neighbours[i] {
+            s0 += w;
+            numerator += w * (values[i] - values[j]) * (values[i] - values[j]);
+        }
+    }
+    if s0 == 0f64 || total_deviation == 0f64 {
+        return 1f64;
+    }
+    (n - 1f64) * numerator / (2f64 * s0 * total_deviation)
+}
+
+/// Calculates the join count statistics for a binary (black/white) variable. The
+/// neighbours of each observation are given as lists of (index, weight) pairs, excluding
+/// the observation itself, and the weights are assumed to be symmetric, such that each
+/// join is counted once. Returns the black-black, white-white and black-white join counts.
+pub fn join_counts(is_black: &[bool], neighbours: &[Vec<(usize, f64)>]) -> (f64, f64, f64) {
+    let mut bb = 0f64;
+    let mut ww = 0f64;
+    let mut bw = 0f64;
+    for i in 0..is_black.len() {
+        for &(j, w) in &neighbours[i] {
+            match (is_black[i], is_black[j]) {
+                (true, true) => bb += w,
+                (false, false) => ww += w,
+                _ => bw += w,
+            }
+        }
+    }
+    (bb / 2f64, ww / 2f64, bw / 2f64)
+}
+
+/// Returns the expected black-black, white-white and black-white join counts under
+/// non-free sampling (i.e. randomization), given the number of black observations,
+/// the total number of observations, and the sum of the (symmetric) weights.
+pub fn expected_join_counts(num_black: f64, n: f64, s0: f64) -> (f64, f64, f64) {
+    let num_white = n - num_black;
+    let denominator = n * (n - 1f64);
+    (
+        s0 / 2f64 * num_black * (num_black - 1f64) / denominator,
+        s0 / 2f64 * num_white * (num_white - 1f64) / denominator,
+        s0 * num_black * num_white / denominator,
+    )
+}
+
+/// Calculates the pseudo p-value of an observed statistic from a set of statistics
+/// simulated by random permutation. The test is one-sided in the direction of the
+/// deviation of the observed value from the mean of the simulated values, i.e.
+/// p = (M + 1) / (R + 1), where M is the number of simulated values that are at least as
+/// extreme as the observed value and R is the number of permutations.
+pub fn permutation_p_value(observed: f64, simulated: &[f64]) -> f64 {
+    let r = simulated.len() as f64;
+    let mean = simulated.iter().sum::<f64>() / r;
+    let m = if observed >= mean {
+        simulated.iter().filter(|&&s| s >= observed).count()
+    } else {
+        simulated.iter().filter(|&&s| s <= observed).count()
+    };
+    (m as f64 + 1f64) / (r + 1f64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chain_neighbours(n: usize) -> Vec<Vec<(usize, f64)>> {
+        (0..n)
+            .map(|i| {
+                let mut nb = vec![];
+                if i > 0 {
+                    nb.push((i - 1, 1f64));
+                }
+                if i < n - 1 {
+                    nb.push((i + 1, 1f64));
+                }
+                nb
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_global_morans_i_and_gearys_c() {
+        let neighbours = chain_neighbours(6);
+        // a smooth trend is positively autocorrelated
+        let smooth = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        assert!(global_morans_i(&smooth, &neighbours) > 0.0);
+        assert!(gearys_c(&smooth, &neighbours) < 1.0);
+        // an alternating pattern is negatively autocorrelated
+        let alternating = [1.0, -1.0, 1.0, -1.0, 1.0, -1.0];
+        assert!((global_morans_i(&alternating, &neighbours) + 1.0).abs() < 1e-12);
+        assert!(gearys_c(&alternating, &neighbours) > 1.0);
+    }
+
+    #[test]
+    fn test_join_counts() {
+        let neighbours = chain_neighbours(4);
+        let (bb, ww, bw) = join_counts(&[true, true, false, false], &neighbours);
+        assert_eq!((bb, ww, bw), (1.0, 1.0, 1.0));
+        // the expected counts sum to the number of joins
+        let (ebb, eww, ebw) = expected_join_counts(2.0, 4.0, 6.0);
+        assert!((ebb + eww + ebw - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_permutation_p_value() {
+        let simulated: Vec<f64> = (0..99).map(|i| i as f64 / 99.0).collect();
+        assert_eq!(permutation_p_value(2.0, &simulated), 0.01);
+        assert_eq!(permutation_p_value(-1.0, &simulated), 0.01);
+    }
+}
@@ -0,0 +1,93 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use crate::structures::Point2D;
+
+/// Calculates the Hausdorff distance between two geometries, i.e. the greatest distance from a
+/// point of either geometry to the nearest point of the other. Each geometry is given as a set of
+/// parts, each of which is a polyline, a ring, or, if it has a single vertex, a point. The distance
+/// is measured from the vertices of each geometry to the segments of the other, which is exact for
+/// points and a close approximation for lines and polygons. Returns `f64::INFINITY` if either
+/// geometry has no vertices.
+pub fn hausdorff_distance(geometry1: &[&[Point2D]], geometry2: &[&[Point2D]]) -> f64 {
+    directed_hausdorff_distance(geometry1, geometry2)
+        .max(directed_hausdorff_distance(geometry2, geometry1))
+}
+
+/// The greatest distance from a vertex of `from` to the nearest point of `to`.
+fn directed_hausdorff_distance(from: &[&[Point2D]], to: &[&[Point2D]]) -> f64 {
+    let mut max_distance = 0f64;
+    let mut num_vertices = 0;
+    for part in from {
+        for p in part.iter() {
+            num_vertices += 1;
+            let d = distance_to_geometry(p, to);
+            if d > max_distance {
+                max_distance = d;
+            }
+        }
+    }
+    if num_vertices == 0 {
+        return f64::INFINITY;
+    }
+    max_distance
+}
+
+fn distance_to_geometry(p: &Point2D, geometry: &[&[Point2D]]) -> f64 {
+    let mut min_distance = f64::INFINITY;
+    for part in geometry {
+        if part.len() == 1 {
+            min_distance = min_distance.min(p.distance(&part[0]));
+        }
+        for segment in part.windows(2) {
+            min_distance = min_distance.min(distance_to_segment(p, &segment[0], &segment[1]));
+        }
+    }
+    min_distance
+}
+
+/// The distance from a point to the nearest point of a line segment.
+fn distance_to_segment(p: &Point2D, start: &Point2D, end: &Point2D) -> f64 {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    let length_squared = dx * dx + dy * dy;
+    if length_squared == 0f64 {
+        return p.distance(start);
+    }
+    let t = (((p.x - start.x) * dx + (p.y - start.y) * dy) / length_squared).clamp(0f64, 1f64);
+    p.distance(&Point2D::new(start.x + t * dx, start.y + t * dy))
+}
+
+#[cfg(test)]
+mod test {
+    use super::hausdorff_distance;
+    use crate::structures::Point2D;
+
+    #[test]
+    fn test_hausdorff_distance() {
+        let line1 = vec![Point2D::new(0.0, 0.0), Point2D::new(10.0, 0.0)];
+        let line2 = vec![
+            Point2D::new(0.0, 1.0),
+            Point2D::new(5.0, 3.0),
+            Point2D::new(10.0, 1.0),
+        ];
+        assert_eq!(hausdorff_distance(&[&line1], &[&line2]), 3.0);
+        assert_eq!(hausdorff_distance(&[&line1], &[&line1]), 0.0);
+
+        // a shorter line lies on the longer one, but the end of the longer is 4 from the shorter
+        let line3 = vec![Point2D::new(2.0, 0.0), Point2D::new(6.0, 0.0)];
+        assert_eq!(hausdorff_distance(&[&line1], &[&line3]), 4.0);
+
+        let points1 = vec![Point2D::new(0.0, 0.0)];
+        let points2 = vec![Point2D::new(3.0, 4.0)];
+        assert_eq!(hausdorff_distance(&[&points1], &[&points2]), 5.0);
+        assert_eq!(hausdorff_distance(&[&points1, &points2], &[&points2]), 5.0);
+
+        let empty: Vec<Point2D> = vec![];
+        assert!(hausdorff_distance(&[&empty], &[&points1]).is_infinite());
+    }
+}
@@ -0,0 +1,119 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+// Bins values into a histogram, returning the counts, the minimum, and the bin width.
+fn histogram(values: &[f64], num_bins: usize) -> Option<(Vec<f64>, f64, f64)> {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    if !min.is_finite() || !max.is_finite() || max <= min || num_bins < 2 {
+        return None;
+    }
+    let width = (max - min) / num_bins as f64;
+    let mut counts = vec![0f64; num_bins];
+    for &v in values {
+        let bin = (((v - min) / width) as usize).min(num_bins - 1);
+        counts[bin] += 1f64;
+    }
+    Some((counts, min, width))
+}
+
+/// Returns the threshold that separates a set of values into two classes using Otsu's method,
+/// i.e. the threshold that maximizes the between-class variance of a histogram with the given
+/// number of bins, or None if the values are all equal. Values greater than the threshold belong
+/// to the upper class.
+///
+/// Otsu, N. (1979). A threshold selection method from gray-level histograms. IEEE Transactions on
+/// Systems, Man, and Cybernetics, 9(1), 62-66.
+pub fn otsu_threshold(values: &[f64], num_bins: usize) -> Option<f64> {
+    let (counts, min, width) = histogram(values, num_bins)?;
+    let total: f64 = counts.iter().sum();
+    let total_sum: f64 = counts.iter().enumerate().map(|(i, c)| i as f64 * c).sum();
+    let (mut n0, mut sum0) = (0f64, 0f64);
+    let mut best = (f64::NEG_INFINITY, 0usize);
+    for (t, count) in counts.iter().enumerate().take(num_bins - 1) {
+        n0 += count;
+        sum0 += t as f64 * count;
+        let n1 = total - n0;
+        if n0 == 0f64 || n1 == 0f64 {
+            continue;
+        }
+        let (m0, m1) = (sum0 / n0, (total_sum - sum0) / n1);
+        let between = n0 * n1 * (m0 - m1) * (m0 - m1);
+        if between > best.0 {
+            best = (between, t);
+        }
+    }
+    Some(min + (best.1 + 1) as f64 * width)
+}
+
+/// Returns the threshold that separates a set of values into two classes using the minimum error
+/// method of Kittler and Illingworth, which models the classes as normal distributions with
+/// different variances, or None if no threshold separates two classes with non-zero variances.
+/// This is more suitable than Otsu's method when the classes differ greatly in size or spread, as
+/// is common for water in images.
+///
+/// Kittler, J., & Illingworth, J. (1986). Minimum error thresholding. Pattern Recognition, 19(1), 41-47.
+pub fn kittler_threshold(values: &[f64], num_bins: usize) -> Option<f64> {
+    let (counts, min, width) = histogram(values, num_bins)?;
+    let total: f64 = counts.iter().sum();
+    let mut best: Option<(f64, usize)> = None;
+    for t in 0..num_bins - 1 {
+        let (n0, s0, ss0) = moments(&counts[..=t], 0);
+        let (n1, s1, ss1) = moments(&counts[t + 1..], t + 1);
+        if n0 == 0f64 || n1 == 0f64 {
+            continue;
+        }
+        let var0 = ss0 / n0 - (s0 / n0) * (s0 / n0);
+        let var1 = ss1 / n1 - (s1 / n1) * (s1 / n1);
+        if var0 <= 0f64 || var1 <= 0f64 {
+            continue;
+        }
+        let (p0, p1) = (n0 / total, n1 / total);
+        let j = 1f64 + p0 * var0.ln() + p1 * var1.ln() - 2f64 * (p0 * p0.ln() + p1 * p1.ln());
+        if best.is_none_or(|b| j < b.0) {
+            best = Some((j, t));
+        }
+    }
+    best.map(|(_, t)| min + (t + 1) as f64 * width)
+}
+
+// Returns the count, and the sums of the bin numbers and of their squares, of the values in a range
+// of the bins of a histogram, of which the first is bin number `first`.
+fn moments(counts: &[f64], first: usize) -> (f64, f64, f64) {
+    counts.iter().enumerate().fold((0f64, 0f64, 0f64), |(n, s, ss), (i, count)| {
+        let bin = (first + i) as f64;
+        (n + count, s + bin * count, ss + bin * bin * count)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{kittler_threshold, otsu_threshold};
+
+    fn bimodal() -> Vec<f64> {
+        // a small, dark class with little spread and a large, bright class with a wide spread
+        let mut values = vec![];
+        for i in 0..200 {
+            values.push(-20.0 + (i % 21) as f64 * 0.1);
+        }
+        for i in 0..2000 {
+            values.push(-8.0 + (i % 81) as f64 * 0.1);
+        }
+        values
+    }
+
+    #[test]
+    fn test_thresholds_separate_classes() {
+        let values = bimodal();
+        let t = otsu_threshold(&values, 256).unwrap();
+        assert!(t > -18.0 && t < -8.0);
+        let t = kittler_threshold(&values, 256).unwrap();
+        assert!(t > -18.0 && t < -8.0);
+        assert!(otsu_threshold(&[1.0, 1.0, 1.0], 256).is_none());
+    }
+}
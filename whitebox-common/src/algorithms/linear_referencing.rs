@@ -0,0 +1,199 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use crate::structures::Point2D;
+
+/// Calculates the cumulative distance along a polyline at each of its vertices,
+/// starting from `start`.
+pub fn cumulative_distances(points: &[Point2D], start: f64) -> Vec<f64> {
+    let mut ret = Vec::with_capacity(points.len());
+    let mut dist = start;
+    for i in 0..points.len() {
+        if i > 0 {
+            dist += points[i].distance(&points[i - 1]);
+        }
+        ret.push(dist);
+    }
+    ret
+}
+
+/// Calibrates the measures of a polyline such that the first vertex is assigned
+/// the measure `from`, the last vertex the measure `to`, and the intermediate
+/// vertices are assigned measures proportional to their distance along the line.
+pub fn calibrate_measures(points: &[Point2D], from: f64, to: f64) -> Vec<f64> {
+    let distances = cumulative_distances(points, 0f64);
+    let length = match distances.last() {
+        Some(d) => *d,
+        None => return vec![],
+    };
+    distances
+        .iter()
+        .map(|d| {
+            if length > 0f64 {
+                from + (to - from) * d / length
+            } else {
+                from
+            }
+        })
+        .collect()
+}
+
+/// Locates the position along a measured polyline with the measure `m`. Measures
+/// may either increase or decrease along the line. Returns the located point and
+/// the index of the segment (i.e. its starting vertex) that contains it, or None
+/// if the measure lies outside of the range of the line's measures.
+pub fn locate_measure(points: &[Point2D], measures: &[f64], m: f64) -> Option<(Point2D, usize)> {
+    if points.len() != measures.len() || points.is_empty() {
+        return None;
+    }
+    if points.len() == 1 {
+        if m == measures[0] {
+            return Some((points[0], 0));
+        }
+        return None;
+    }
+    for i in 0..points.len() - 1 {
+        let (m1, m2) = (measures[i], measures[i + 1]);
+        if m >= m1.min(m2) && m <= m1.max(m2) {
+            let t = if m2 != m1 { (m - m1) / (m2 - m1) } else { 0f64 };
+            return Some((interpolate(&points[i], &points[i + 1], t), i));
+        }
+    }
+    None
+}
+
+/// Extracts the portion of a measured polyline lying between the measures `from` and
+/// `to`. The returned vertices retain the direction of the input line. If the measure
+/// range covers more than one non-contiguous portion of the line, only the first
+/// portion is returned. Returns None if the measure range does not overlap the line
+/// by more than a single point.
+pub fn extract_measure_range(
+    points: &[Point2D],
+    measures: &[f64],
+    from: f64,
+    to: f64,
+) -> Option<(Vec<Point2D>, Vec<f64>)> {
+    if points.len() != measures.len() || points.len() < 2 {
+        return None;
+    }
+    let (lo, hi) = (from.min(to), from.max(to));
+    let mut out_points: Vec<Point2D> = vec![];
+    let mut out_measures: Vec<f64> = vec![];
+    for i in 0..points.len() - 1 {
+        let (m1, m2) = (measures[i], measures[i + 1]);
+        let seg_lo = m1.min(m2);
+        let seg_hi = m1.max(m2);
+        if seg_hi < lo || seg_lo > hi {
+            if !out_points.is_empty() {
+                break; // the end of the first contiguous portion
+            }
+            continue;
+        }
+        // the measures at which the line enters and exits the range in this segment
+        let (entry_m, exit_m) = if m2 >= m1 {
+            (m1.max(lo), m2.min(hi))
+        } else {
+            (m1.min(hi), m2.max(lo))
+        };
+        let t_entry = if m2 != m1 { (entry_m - m1) / (m2 - m1) } else { 0f64 };
+        let t_exit = if m2 != m1 { (exit_m - m1) / (m2 - m1) } else { 1f64 };
+        let entry = interpolate(&points[i], &points[i + 1], t_entry);
+        let exit = interpolate(&points[i], &points[i + 1], t_exit);
+        if out_points.is_empty() || !out_points[out_points.len() - 1].nearly_equals(&entry) {
+            out_points.push(entry);
+            out_measures.push(entry_m);
+        }
+        if !out_points[out_points.len() - 1].nearly_equals(&exit) {
+            out_points.push(exit);
+            out_measures.push(exit_m);
+        }
+    }
+    if out_points.len() < 2 {
+        return None;
+    }
+    Some((out_points, out_measures))
+}
+
+/// Offsets a point perpendicular to the direction of the segment from `p1` to `p2`.
+/// Positive offsets are to the right of the segment direction and negative offsets
+/// to the left.
+pub fn offset_point(p: &Point2D, p1: &Point2D, p2: &Point2D, offset: f64) -> Point2D {
+    let dx = p2.x - p1.x;
+    let dy = p2.y - p1.y;
+    let length = (dx * dx + dy * dy).sqrt();
+    if length == 0f64 || offset == 0f64 {
+        return *p;
+    }
+    Point2D::new(p.x + offset * dy / length, p.y - offset * dx / length)
+}
+
+fn interpolate(p1: &Point2D, p2: &Point2D, t: f64) -> Point2D {
+    Point2D::new(p1.x + t * (p2.x - p1.x), p1.y + t * (p2.y - p1.y))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::structures::Point2D;
+
+    fn route() -> Vec<Point2D> {
+        vec![
+            Point2D::new(0.0, 0.0),
+            Point2D::new(10.0, 0.0),
+            Point2D::new(10.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn test_calibrate_measures() {
+        let measures = calibrate_measures(&route(), 100.0, 300.0);
+        assert_eq!(measures, vec![100.0, 200.0, 300.0]);
+        assert_eq!(cumulative_distances(&route(), 5.0), vec![5.0, 15.0, 25.0]);
+    }
+
+    #[test]
+    fn test_locate_measure() {
+        let measures = vec![0.0, 10.0, 20.0];
+        let (p, seg) = locate_measure(&route(), &measures, 15.0).unwrap();
+        assert_eq!(seg, 1);
+        assert!(p.nearly_equals(&Point2D::new(10.0, 5.0)));
+        // decreasing measures
+        let measures = vec![20.0, 10.0, 0.0];
+        let (p, _) = locate_measure(&route(), &measures, 15.0).unwrap();
+        assert!(p.nearly_equals(&Point2D::new(5.0, 0.0)));
+        assert!(locate_measure(&route(), &measures, 25.0).is_none());
+    }
+
+    #[test]
+    fn test_extract_measure_range() {
+        let measures = vec![0.0, 10.0, 20.0];
+        let (points, m) = extract_measure_range(&route(), &measures, 5.0, 15.0).unwrap();
+        assert_eq!(points.len(), 3);
+        assert!(points[0].nearly_equals(&Point2D::new(5.0, 0.0)));
+        assert!(points[1].nearly_equals(&Point2D::new(10.0, 0.0)));
+        assert!(points[2].nearly_equals(&Point2D::new(10.0, 5.0)));
+        assert_eq!(m, vec![5.0, 10.0, 15.0]);
+        // a range extending past the end of the line is clipped
+        let (points, _) = extract_measure_range(&route(), &measures, 12.0, 50.0).unwrap();
+        assert_eq!(points.len(), 2);
+        assert!(points[1].nearly_equals(&Point2D::new(10.0, 10.0)));
+        assert!(extract_measure_range(&route(), &measures, 30.0, 50.0).is_none());
+    }
+
+    #[test]
+    fn test_offset_point() {
+        let p = offset_point(
+            &Point2D::new(5.0, 0.0),
+            &Point2D::new(0.0, 0.0),
+            &Point2D::new(10.0, 0.0),
+            2.0,
+        );
+        // right of an eastward line is south
+        assert!(p.nearly_equals(&Point2D::new(5.0, -2.0)));
+    }
+}
@@ -0,0 +1,165 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+/// Calculates the Getis-Ord Gi* statistic, expressed as a z-score, for an observation
+/// given the weighted sum of the values within its neighbourhood (including itself),
+/// the sum of the weights, the sum of the squared weights, and the global mean,
+/// standard deviation and number of observations.
+pub fn gi_star_z_score(
+    sum_wx: f64,
+    sum_w: f64,
+    sum_w2: f64,
+    mean: f64,
+    std_dev: f64,
+    n: f64,
+) -> f64 {
+    let denominator = std_dev * ((n * sum_w2 - sum_w * sum_w) / (n - 1f64)).sqrt();
+    if denominator > 0f64 {
+        (sum_wx - mean * sum_w) / denominator
+    } else {
+        0f64
+    }
+}
+
+/// Calculates the expected value and variance of Anselin's (1995) Local Moran's I
+/// under the randomization assumption, given the number of observations, the sample
+/// kurtosis (b2) of the values, and the sum of the weights and squared weights of the
+/// observation's neighbours (excluding itself).
+pub fn local_morans_i_moments(n: f64, b2: f64, sum_w: f64, sum_w2: f64) -> (f64, f64) {
+    let expected = -sum_w / (n - 1f64);
+    let variance = sum_w2 * (n - b2) / (n - 1f64)
+        + (sum_w * sum_w - sum_w2) * (2f64 * b2 - n) / ((n - 1f64) * (n - 2f64))
+        - expected * expected;
+    (expected, variance)
+}
+
+/// Calculates the Getis-Ord Gi* z-scores for a set of observations. The neighbours of
+/// each observation are given as lists of (index, weight) pairs, excluding the
+/// observation itself, which is assigned a weight of one.
+pub fn local_gi_star(values: &[f64], neighbours: &[Vec<(usize, f64)>]) -> Vec<f64> {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std_dev = (values.iter().map(|v| v * v).sum::<f64>() / n - mean * mean)
+        .max(0f64)
+        .sqrt();
+    let mut z_scores = Vec::with_capacity(values.len());
+    for i in 0..values.len() {
+        let mut sum_wx = values[i];
+        let mut sum_w = 1f64;
+        let mut sum_w2 = 1f64;
+        for &(j, w) in &neighbours[i] {
+            sum_wx += w * values[j];
+            sum_w += w;
+            sum_w2 += w * w;
+        }
+        z_scores.push(gi_star_z_score(sum_wx, sum_w, sum_w2, mean, std_dev, n));
+    }
+    z_scores
+}
+
+/// Calculates Anselin's (1995) Local Moran's I for a set of observations. The neighbours
+/// of each observation are given as lists of (index, weight) pairs, excluding the
+/// observation itself. Returns the local index and its z-score for each observation.
+pub fn local_morans_i(values: &[f64], neighbours: &[Vec<(usize, f64)>]) -> Vec<(f64, f64)> {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let m2 = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    let m4 = values.iter().map(|v| (v - mean).powi(4)).sum::<f64>() / n;
+    let b2 = if m2 > 0f64 { m4 / (m2 * m2) } else { 0f64 };
+    let mut ret = Vec::with_capacity(values.len());
+    for i in 0..values.len() {
+        let mut lag = 0f64;
+        let mut sum_w = 0f64;
+        let mut sum_w2 = 0f64;
+        for &(j, w) in &neighbours[i] {
+            lag += w * (values[j] - mean);
+            sum_w += w;
+            sum_w2 += w * w;
+        }
+        let local_i = if m2 > 0f64 {
+            (values[i] - mean) / m2 * lag
+        } else {
+            0f64
+        };
+        let (expected, variance) = local_morans_i_moments(n, b2, sum_w, sum_w2);
+        let z = if variance > 0f64 {
+            (local_i - expected) / variance.sqrt()
+        } else {
+            0f64
+        };
+        ret.push((local_i, z));
+    }
+    ret
+}
+
+/// Returns the critical p-value for a set of simultaneous tests, controlling the false
+/// discovery rate (FDR) at the level `alpha` using the Benjamini-Hochberg procedure.
+/// Tests with p-values less than or equal to the returned value are significant. If no
+/// tests are significant, zero is returned.
+pub fn fdr_critical_p_value(p_values: &[f64], alpha: f64) -> f64 {
+    let mut sorted: Vec<f64> = p_values.iter().cloned().filter(|p| !p.is_nan()).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let m = sorted.len() as f64;
+    let mut critical = 0f64;
+    for (k, &p) in sorted.iter().enumerate() {
+        if p <= (k + 1) as f64 / m * alpha {
+            critical = p;
+        }
+    }
+    critical
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chain_neighbours(n: usize) -> Vec<Vec<(usize, f64)>> {
+        // each observation neighbours the previous and next observations
+        (0..n)
+            .map(|i| {
+                let mut nb = vec![];
+                if i > 0 {
+                    nb.push((i - 1, 1f64));
+                }
+                if i < n - 1 {
+                    nb.push((i + 1, 1f64));
+                }
+                nb
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_local_gi_star() {
+        let values = [1.0, 1.0, 1.0, 1.0, 1.0, 10.0, 10.0, 10.0];
+        let z = local_gi_star(&values, &chain_neighbours(values.len()));
+        // the cluster of high values is a hot spot and the low values a cold spot
+        assert!(z[6] > 1.5);
+        assert!(z[1] < 0.0);
+    }
+
+    #[test]
+    fn test_local_morans_i() {
+        let values = [1.0, 1.0, 1.0, 1.0, 10.0, 10.0, 10.0, 10.0];
+        let neighbours = chain_neighbours(values.len());
+        let res = local_morans_i(&values, &neighbours);
+        // similar neighbouring values have positive local I
+        assert!(res[1].0 > 0.0 && res[6].0 > 0.0);
+        // an outlier has negative local I
+        let values = [1.0, 1.0, 1.0, 10.0, 1.0, 1.0, 1.0, 1.0];
+        let res = local_morans_i(&values, &neighbours);
+        assert!(res[3].0 < 0.0);
+    }
+
+    #[test]
+    fn test_fdr_critical_p_value() {
+        let p = [0.001, 0.008, 0.039, 0.041, 0.042, 0.06, 0.074, 0.205];
+        assert_eq!(fdr_critical_p_value(&p, 0.05), 0.008);
+        assert_eq!(fdr_critical_p_value(&[0.5, 0.9], 0.05), 0.0);
+    }
+}
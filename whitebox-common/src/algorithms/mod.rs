@@ -6,31 +6,58 @@ Last Modified: 25/09/2018
 License: MIT
 */
 // private sub-module defined in other files
+mod centrography;
 mod convex_hull;
 mod delaunay_triangulation;
+mod global_autocorrelation;
+mod hausdorff;
+mod histogram_thresholds;
 mod is_clockwise_order;
 mod line_ops;
+mod linear_referencing;
+mod local_autocorrelation;
 // mod lzw;
 mod minimum_bounding_box;
+mod morphology;
 mod poly_area;
 mod poly_ops;
 mod poly_perimeter;
+mod skeleton;
 mod smallest_enclosing_circle;
 
 // exports identifiers from private sub-modules in the current module namespace
+pub use self::centrography::{
+    median_center, standard_deviational_ellipse, standard_distance, weighted_mean_center,
+};
 pub use self::convex_hull::convex_hull;
 pub use self::delaunay_triangulation::{triangulate, Triangulation};
 pub use self::delaunay_triangulation::EMPTY;
+pub use self::global_autocorrelation::{
+    expected_join_counts, gearys_c, global_morans_i, join_counts, permutation_p_value,
+};
+pub use self::hausdorff::hausdorff_distance;
+pub use self::histogram_thresholds::{kittler_threshold, otsu_threshold};
 pub use self::is_clockwise_order::is_clockwise_order;
 pub use self::line_ops::{
     do_polylines_intersect, find_line_intersections, find_split_points_at_line_intersections,
     point_line_distance, simplify_rdp,
 };
+pub use self::linear_referencing::{
+    calibrate_measures, cumulative_distances, extract_measure_range, locate_measure, offset_point,
+};
 // pub use self::lzw::{lzw_decode, lzw_encode};
+pub use self::local_autocorrelation::{
+    fdr_critical_p_value, gi_star_z_score, local_gi_star, local_morans_i, local_morans_i_moments,
+};
 pub use self::minimum_bounding_box::{minimum_bounding_box, MinimizationCriterion};
+pub use self::morphology::{
+    black_tophat, closing, dilate, erode, opening, reconstruct_by_dilation, reconstruct_by_erosion,
+    white_tophat, StructuringElement,
+};
 pub use self::poly_area::polygon_area;
 pub use self::poly_ops::{
     interior_point, point_in_poly, poly_in_poly, poly_is_convex, poly_overlaps_poly, winding_number,
 };
 pub use self::poly_perimeter::polygon_perimeter;
+pub use self::skeleton::{distance_to_background, zhang_suen_thinning};
 pub use self::smallest_enclosing_circle::smallest_enclosing_circle;
@@ -0,0 +1,413 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use crate::structures::Array2D;
+use std::collections::VecDeque;
+use std::io::Error;
+
+/// The shape of a morphological structuring element, given as the (row, column) offsets
+/// of its members relative to its origin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructuringElement {
+    pub offsets: Vec<(isize, isize)>,
+}
+
+impl StructuringElement {
+    /// Creates a rectangular structuring element with the given (odd) numbers of rows and columns.
+    pub fn rectangle(rows: usize, columns: usize) -> StructuringElement {
+        let (mid_r, mid_c) = ((rows / 2) as isize, (columns / 2) as isize);
+        let mut offsets = vec![];
+        for r in -mid_r..=mid_r {
+            for c in -mid_c..=mid_c {
+                offsets.push((r, c));
+            }
+        }
+        StructuringElement { offsets }
+    }
+
+    /// Creates a disk-shaped structuring element of the given (odd) diameter.
+    pub fn disk(size: usize) -> StructuringElement {
+        let mid = (size / 2) as isize;
+        let radius_sqr = (size as f64 / 2f64) * (size as f64 / 2f64);
+        let mut offsets = vec![];
+        for r in -mid..=mid {
+            for c in -mid..=mid {
+                if ((r * r + c * c) as f64) <= radius_sqr {
+                    offsets.push((r, c));
+                }
+            }
+        }
+        StructuringElement { offsets }
+    }
+
+    /// Creates a diamond-shaped structuring element of the given (odd) width.
+    pub fn diamond(size: usize) -> StructuringElement {
+        let mid = (size / 2) as isize;
+        let mut offsets = vec![];
+        for r in -mid..=mid {
+            for c in -mid..=mid {
+                if r.abs() + c.abs() <= mid {
+                    offsets.push((r, c));
+                }
+            }
+        }
+        StructuringElement { offsets }
+    }
+
+    /// Creates a cross-shaped structuring element of the given (odd) width.
+    pub fn cross(size: usize) -> StructuringElement {
+        let mid = (size / 2) as isize;
+        let mut offsets = vec![];
+        for r in -mid..=mid {
+            for c in -mid..=mid {
+                if r == 0 || c == 0 {
+                    offsets.push((r, c));
+                }
+            }
+        }
+        StructuringElement { offsets }
+    }
+
+    /// Creates a structuring element from a matrix, in which the members are the true
+    /// entries. The origin is the centre of the matrix, which must have odd dimensions.
+    pub fn from_matrix(matrix: &[Vec<bool>]) -> Option<StructuringElement> {
+        let rows = matrix.len();
+        let columns = if rows > 0 { matrix[0].len() } else { 0 };
+        if rows.is_multiple_of(2) || columns.is_multiple_of(2) || matrix.iter().any(|row| row.len() != columns) {
+            return None;
+        }
+        let (mid_r, mid_c) = ((rows / 2) as isize, (columns / 2) as isize);
+        let mut offsets = vec![];
+        for (r, row) in matrix.iter().enumerate() {
+            for (c, &member) in row.iter().enumerate() {
+                if member {
+                    offsets.push((r as isize - mid_r, c as isize - mid_c));
+                }
+            }
+        }
+        if offsets.is_empty() {
+            return None;
+        }
+        Some(StructuringElement { offsets })
+    }
+
+    /// Returns the structuring element reflected through its origin.
+    pub fn reflect(&self) -> StructuringElement {
+        StructuringElement {
+            offsets: self.offsets.iter().map(|&(r, c)| (-r, -c)).collect(),
+        }
+    }
+}
+
+// Takes the minimum (sign = 1) or maximum (sign = -1) of the valid cells under the element.
+fn rank_filter(
+    image: &Array2D<f64>,
+    element: &StructuringElement,
+    sign: f64,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    let nodata = image.nodata;
+    let mut output = image.duplicate();
+    for row in 0..image.rows {
+        check()?;
+        for col in 0..image.columns {
+            if image.get_value(row, col) == nodata {
+                continue;
+            }
+            let mut extreme = f64::INFINITY;
+            for &(r, c) in &element.offsets {
+                let (rn, cn) = (row + r, col + c);
+                if rn < 0 || rn >= image.rows || cn < 0 || cn >= image.columns {
+                    continue;
+                }
+                let z = image.get_value(rn, cn);
+                if z != nodata && sign * z < extreme {
+                    extreme = sign * z;
+                }
+            }
+            output.set_value(row, col, if extreme.is_finite() { sign * extreme } else { nodata });
+        }
+    }
+    Ok(output)
+}
+
+/// Erodes an image with a structuring element, i.e. replaces each cell with the minimum of the
+/// cells under the element. Nodata cells are ignored and are unchanged in the output. For a
+/// binary image of zeros and ones, this is the binary erosion.
+///
+/// The `check` function is called once per row, and the erosion stops with its error, e.g. when a
+/// tool is cancelled. This is also true of the other operations of this module.
+pub fn erode(
+    image: &Array2D<f64>,
+    element: &StructuringElement,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    rank_filter(image, element, 1f64, check)
+}
+
+/// Dilates an image with a structuring element, i.e. replaces each cell with the maximum of the
+/// cells under the reflected element. Nodata cells are ignored and are unchanged in the output.
+pub fn dilate(
+    image: &Array2D<f64>,
+    element: &StructuringElement,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    rank_filter(image, &element.reflect(), -1f64, check)
+}
+
+/// The opening of an image, i.e. the dilation of its erosion, which removes bright features
+/// that the structuring element does not fit within.
+pub fn opening(
+    image: &Array2D<f64>,
+    element: &StructuringElement,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    dilate(&erode(image, element, check)?, element, check)
+}
+
+/// The closing of an image, i.e. the erosion of its dilation, which removes dark features
+/// that the structuring element does not fit within.
+pub fn closing(
+    image: &Array2D<f64>,
+    element: &StructuringElement,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    erode(&dilate(image, element, check)?, element, check)
+}
+
+/// The white top-hat transform of an image, i.e. the difference between the image and its opening.
+pub fn white_tophat(
+    image: &Array2D<f64>,
+    element: &StructuringElement,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    Ok(difference(image, &opening(image, element, check)?))
+}
+
+/// The black top-hat transform of an image, i.e. the difference between its closing and the image.
+pub fn black_tophat(
+    image: &Array2D<f64>,
+    element: &StructuringElement,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    Ok(difference(&closing(image, element, check)?, image))
+}
+
+fn difference(a: &Array2D<f64>, b: &Array2D<f64>) -> Array2D<f64> {
+    let nodata = a.nodata;
+    let mut output = a.duplicate();
+    for row in 0..a.rows {
+        for col in 0..a.columns {
+            let z = a.get_value(row, col);
+            if z != nodata {
+                output.set_value(row, col, z - b.get_value(row, col));
+            }
+        }
+    }
+    output
+}
+
+/// The morphological reconstruction by dilation of a marker image under a mask image, i.e. the
+/// repeated 8-connected dilation of the marker, limited at each step by the mask, until stability.
+/// Marker values above the mask are first lowered to it. Regional maxima of the mask that are not
+/// marked are removed, e.g. reconstructing a DEM from the DEM lowered by h removes peaks of height
+/// less than h. Nodata cells in the mask are barriers that are unchanged in the output.
+///
+/// Uses the hybrid algorithm of Vincent, L. (1993). Morphological grayscale reconstruction in image
+/// analysis: applications and efficient algorithms. IEEE Transactions on Image Processing, 2(2), 176-201.
+pub fn reconstruct_by_dilation(
+    marker: &Array2D<f64>,
+    mask: &Array2D<f64>,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    reconstruct(marker, mask, 1f64, check)
+}
+
+/// The morphological reconstruction by erosion of a marker image above a mask image, the dual of
+/// `reconstruct_by_dilation`. Reconstructing a DEM from a marker equal to the DEM along its edges
+/// and to its maximum elsewhere fills the DEM's depressions.
+pub fn reconstruct_by_erosion(
+    marker: &Array2D<f64>,
+    mask: &Array2D<f64>,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    reconstruct(marker, mask, -1f64, check)
+}
+
+// Reconstruction by dilation of the images multiplied by sign.
+fn reconstruct(
+    marker: &Array2D<f64>,
+    mask: &Array2D<f64>,
+    sign: f64,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<Array2D<f64>, Error> {
+    const PRIOR: [(isize, isize); 4] = [(-1, -1), (-1, 0), (-1, 1), (0, -1)];
+    const LATER: [(isize, isize); 4] = [(0, 1), (1, -1), (1, 0), (1, 1)];
+    let nodata = mask.nodata;
+    let (rows, columns) = (mask.rows, mask.columns);
+    let is_valid = |r: isize, c: isize| {
+        r >= 0 && r < rows && c >= 0 && c < columns && mask.get_value(r, c) != nodata
+    };
+    // the sign-adjusted mask and result
+    let i = |r: isize, c: isize| sign * mask.get_value(r, c);
+    let mut j = mask.duplicate();
+    for row in 0..rows {
+        for col in 0..columns {
+            if is_valid(row, col) {
+                let m = marker.get_value(row, col);
+                let m = if m == marker.nodata { f64::NEG_INFINITY } else { sign * m };
+                j.set_value(row, col, m.min(i(row, col)));
+            }
+        }
+    }
+
+    // forward raster scan
+    for row in 0..rows {
+        check()?;
+        for col in 0..columns {
+            if !is_valid(row, col) {
+                continue;
+            }
+            let mut z = j.get_value(row, col);
+            for &(dr, dc) in &PRIOR {
+                if is_valid(row + dr, col + dc) {
+                    z = z.max(j.get_value(row + dr, col + dc));
+                }
+            }
+            j.set_value(row, col, z.min(i(row, col)));
+        }
+    }
+
+    // backward raster scan, queueing the cells that may propagate further
+    let mut queue = VecDeque::new();
+    for row in (0..rows).rev() {
+        check()?;
+        for col in (0..columns).rev() {
+            if !is_valid(row, col) {
+                continue;
+            }
+            let mut z = j.get_value(row, col);
+            for &(dr, dc) in &LATER {
+                if is_valid(row + dr, col + dc) {
+                    z = z.max(j.get_value(row + dr, col + dc));
+                }
+            }
+            let z = z.min(i(row, col));
+            j.set_value(row, col, z);
+            for &(dr, dc) in &LATER {
+                let (r, c) = (row + dr, col + dc);
+                if is_valid(r, c) && j.get_value(r, c) < z && j.get_value(r, c) < i(r, c) {
+                    queue.push_back((row, col));
+                    break;
+                }
+            }
+        }
+    }
+
+    // propagation
+    let mut num_popped = 0usize;
+    while let Some((row, col)) = queue.pop_front() {
+        num_popped += 1;
+        if num_popped.is_multiple_of(columns.max(1) as usize) {
+            check()?;
+        }
+        let z = j.get_value(row, col);
+        for &(dr, dc) in PRIOR.iter().chain(LATER.iter()) {
+            let (r, c) = (row + dr, col + dc);
+            if !is_valid(r, c) {
+                continue;
+            }
+            let zn = j.get_value(r, c);
+            if zn < z && zn != i(r, c) {
+                j.set_value(r, c, z.min(i(r, c)));
+                queue.push_back((r, c));
+            }
+        }
+    }
+
+    for row in 0..rows {
+        for col in 0..columns {
+            if is_valid(row, col) {
+                let z = j.get_value(row, col);
+                j.set_value(row, col, sign * z);
+            }
+        }
+    }
+    Ok(j)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn image(values: &[&[f64]]) -> Array2D<f64> {
+        let mut a: Array2D<f64> =
+            Array2D::new(values.len() as isize, values[0].len() as isize, 0f64, -1f64).unwrap();
+        for (r, row) in values.iter().enumerate() {
+            a.set_row_data(r as isize, row.to_vec());
+        }
+        a
+    }
+
+    #[test]
+    fn test_opening_removes_small_features() {
+        let a = image(&[
+            &[0., 0., 0., 0., 0., 0.],
+            &[0., 1., 0., 0., 0., 0.],
+            &[0., 0., 0., 1., 1., 1.],
+            &[0., 0., 0., 1., 1., 1.],
+            &[0., 0., 0., 1., 1., 1.],
+        ]);
+        let opened = opening(&a, &StructuringElement::rectangle(3, 3), &|| Ok(())).unwrap();
+        assert_eq!(opened.get_value(1, 1), 0.);
+        for r in 2..5 {
+            for c in 3..6 {
+                assert_eq!(opened.get_value(r, c), 1.);
+            }
+        }
+        let tophat = white_tophat(&a, &StructuringElement::rectangle(3, 3), &|| Ok(())).unwrap();
+        assert_eq!(tophat.get_value(1, 1), 1.);
+        assert_eq!(tophat.get_value(3, 4), 0.);
+    }
+
+    #[test]
+    fn test_structuring_elements() {
+        assert_eq!(StructuringElement::rectangle(3, 5).offsets.len(), 15);
+        assert_eq!(StructuringElement::diamond(5).offsets.len(), 13);
+        assert_eq!(StructuringElement::cross(5).offsets.len(), 9);
+        assert_eq!(StructuringElement::disk(5).offsets.len(), 21);
+        let e = StructuringElement::from_matrix(&[vec![false, true, true]]).unwrap();
+        assert_eq!(e.offsets, vec![(0, 0), (0, 1)]);
+        assert!(StructuringElement::from_matrix(&[vec![true, true]]).is_none());
+    }
+
+    #[test]
+    fn test_reconstruction_fills_depressions() {
+        let dem = image(&[
+            &[5., 5., 5., 5., 5.],
+            &[5., 2., 3., 1., 5.],
+            &[5., 4., 6., 2., 4.],
+            &[5., 5., 5., 5., 5.],
+        ]);
+        let mut marker = dem.duplicate();
+        for r in 1..3 {
+            for c in 1..4 {
+                marker.set_value(r, c, 10.);
+            }
+        }
+        let filled = reconstruct_by_erosion(&marker, &dem, &|| Ok(())).unwrap();
+        assert_eq!(filled.get_row_data(1), vec![5., 4., 4., 4., 5.]);
+        assert_eq!(filled.get_row_data(2), vec![5., 4., 6., 4., 4.]);
+
+        // reconstruction by dilation of the DEM lowered by 1 removes peaks lower than 1
+        let mut lowered = dem.duplicate();
+        lowered.set_value(2, 2, 5.);
+        let recon = reconstruct_by_dilation(&lowered, &dem, &|| Ok(())).unwrap();
+        assert_eq!(recon.get_value(2, 2), 5.);
+    }
+}
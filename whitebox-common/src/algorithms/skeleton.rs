@@ -0,0 +1,205 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use crate::structures::Array2D;
+use std::io::Error;
+
+/// Thins the foreground (value 1) of a binary image to a one-cell wide, 8-connected skeleton
+/// in place, using the two-subiteration algorithm of Zhang, T.Y. and Suen, C.Y. (1984). A fast
+/// parallel algorithm for thinning digital patterns. Communications of the ACM, 27(3), 236-239.
+/// Cells outside of the image are treated as background. Returns the number of iterations. The
+/// `check` function is called once per row of each iteration, and the thinning stops with its
+/// error, e.g. when a tool is cancelled.
+pub fn zhang_suen_thinning(
+    image: &mut Array2D<u8>,
+    check: &dyn Fn() -> Result<(), Error>,
+) -> Result<usize, Error> {
+    // neighbours P2 to P9, clockwise from the north
+    const OFFSETS: [(isize, isize); 8] = [
+        (-1, 0),
+        (-1, 1),
+        (0, 1),
+        (1, 1),
+        (1, 0),
+        (1, -1),
+        (0, -1),
+        (-1, -1),
+    ];
+    let (rows, columns) = (image.rows, image.columns);
+    let mut iterations = 0;
+    let mut to_delete = vec![];
+    loop {
+        iterations += 1;
+        let mut changed = false;
+        for step in 0..2 {
+            to_delete.clear();
+            for row in 0..rows {
+                check()?;
+                for col in 0..columns {
+                    if image.get_value(row, col) != 1 {
+                        continue;
+                    }
+                    let mut p = [0u8; 8];
+                    for i in 0..8 {
+                        let (r, c) = (row + OFFSETS[i].0, col + OFFSETS[i].1);
+                        if r >= 0 && r < rows && c >= 0 && c < columns && image.get_value(r, c) == 1 {
+                            p[i] = 1;
+                        }
+                    }
+                    let b: u8 = p.iter().sum();
+                    if !(2..=6).contains(&b) {
+                        continue;
+                    }
+                    // number of 0 to 1 transitions in the ordered sequence P2, P3, ..., P9, P2
+                    let a = (0..8).filter(|&i| p[i] == 0 && p[(i + 1) % 8] == 1).count();
+                    if a != 1 {
+                        continue;
+                    }
+                    let (p2, p4, p6, p8) = (p[0], p[2], p[4], p[6]);
+                    let remove = if step == 0 {
+                        p2 * p4 * p6 == 0 && p4 * p6 * p8 == 0
+                    } else {
+                        p2 * p4 * p8 == 0 && p2 * p6 * p8 == 0
+                    };
+                    if remove {
+                        to_delete.push((row, col));
+                    }
+                }
+            }
+            for &(row, col) in &to_delete {
+                image.set_value(row, col, 0);
+            }
+            changed |= !to_delete.is_empty();
+        }
+        if !changed {
+            break;
+        }
+    }
+    Ok(iterations)
+}
+
+/// Calculates the exact Euclidean distance from the centre of each foreground (non-zero) cell
+/// of an image to the centre of the nearest background (zero) cell, in the units of the cell
+/// resolutions. Background cells have a distance of zero. Cells outside of the image are not
+/// background, and so the foreground cells of an image with no background cells have infinite
+/// distances. Uses the separable algorithm of Felzenszwalb, P.F. and Huttenlocher, D.P. (2012).
+/// Distance transforms of sampled functions. Theory of Computing, 8, 415-428.
+pub fn distance_to_background(
+    image: &Array2D<u8>,
+    resolution_x: f64,
+    resolution_y: f64,
+) -> Array2D<f64> {
+    let (rows, columns) = (image.rows, image.columns);
+    let mut sqr_dist: Array2D<f64> =
+        Array2D::new(rows, columns, f64::INFINITY, f64::INFINITY).unwrap();
+    for row in 0..rows {
+        for col in 0..columns {
+            if image.get_value(row, col) == 0 {
+                sqr_dist.set_value(row, col, 0f64);
+            }
+        }
+    }
+    let mut f = vec![];
+    // columns, then rows
+    for col in 0..columns {
+        f.clear();
+        f.extend((0..rows).map(|row| sqr_dist.get_value(row, col)));
+        let d = squared_distance_1d(&f, resolution_y);
+        for row in 0..rows {
+            sqr_dist.set_value(row, col, d[row as usize]);
+        }
+    }
+    for row in 0..rows {
+        let d = squared_distance_1d(&sqr_dist.get_row_data(row), resolution_x);
+        sqr_dist.set_row_data(row, d.iter().map(|v| v.sqrt()).collect());
+    }
+    sqr_dist
+}
+
+// The lower envelope of the parabolas (x - q)^2 * spacing^2 + f(q).
+fn squared_distance_1d(f: &[f64], spacing: f64) -> Vec<f64> {
+    let n = f.len();
+    let s2 = spacing * spacing;
+    let mut d = vec![f64::INFINITY; n];
+    let mut v = vec![0usize; n]; // locations of the envelope's parabolas
+    let mut z = vec![0f64; n + 1]; // boundaries between them
+    let mut k = 0usize;
+    let mut started = false;
+    for q in 0..n {
+        if !f[q].is_finite() {
+            continue;
+        }
+        if !started {
+            v[0] = q;
+            z[0] = f64::NEG_INFINITY;
+            z[1] = f64::INFINITY;
+            started = true;
+            continue;
+        }
+        loop {
+            let p = v[k];
+            let s = ((f[q] + s2 * (q * q) as f64) - (f[p] + s2 * (p * p) as f64))
+                / (2f64 * s2 * (q as f64 - p as f64));
+            if s <= z[k] && k > 0 {
+                k -= 1;
+            } else {
+                k += 1;
+                v[k] = q;
+                z[k] = s;
+                z[k + 1] = f64::INFINITY;
+                break;
+            }
+        }
+    }
+    if !started {
+        return d;
+    }
+    k = 0;
+    for (q, dq) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let dx = q as f64 - v[k] as f64;
+        *dq = s2 * dx * dx + f[v[k]];
+    }
+    d
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_thinning_a_bar() {
+        let mut image: Array2D<u8> = Array2D::new(7, 12, 0, 0).unwrap();
+        for row in 1..6 {
+            for col in 1..11 {
+                image.set_value(row, col, 1);
+            }
+        }
+        zhang_suen_thinning(&mut image, &|| Ok(())).unwrap();
+        // the skeleton of the bar is a line along its middle row
+        for col in 0..12 {
+            assert_eq!(image.get_value(3, col), if (3..8).contains(&col) { 1 } else { 0 });
+            for row in [0, 1, 2, 4, 5, 6] {
+                assert_eq!(image.get_value(row, col), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_distance_to_background() {
+        let mut image: Array2D<u8> = Array2D::new(9, 9, 1, 0).unwrap();
+        image.set_value(4, 4, 0);
+        let dist = distance_to_background(&image, 2.0, 1.0);
+        assert_eq!(dist.get_value(4, 4), 0.0);
+        assert!((dist.get_value(4, 7) - 6.0).abs() < 1e-9);
+        assert!((dist.get_value(0, 4) - 4.0).abs() < 1e-9);
+        assert!((dist.get_value(1, 2) - 5.0).abs() < 1e-9);
+    }
+}
@@ -1,18 +1,180 @@
+/*
+NOTES: The settings are layered, with each layer overriding those below it:
+
+    1. the built-in defaults (`Configs::new`);
+    2. the settings.json file in the WhiteboxTools directory, which is updated by the
+       command-line flags that are used without --run;
+    3. the configuration file, i.e. the file named by the WBT_CONFIG_FILE environment variable
+       or, if it is unset, config.json within the WhiteboxTools directory of the user's
+       configuration directory, which sets any subset of the settings using the keys of
+       settings.json, e.g. { "max_procs": 4, "geotiff_compression": "zstd" };
+    4. environment variables named by the upper-case setting prefixed by WBT_, e.g.
+       WBT_MAX_PROCS=4 or WBT_WORKING_DIRECTORY=/data/project;
+    5. the command-line flags used with --run, which apply to that run only.
+
+This allows global defaults, e.g. those of a shared workstation, a container image or a batch
+job, to be set once rather than passed with every call.
+*/
+
 use serde_json;
+use serde_json::{Map, Value};
 use serde::{Deserialize, Serialize};
+use std::env;
 use std::fs;
 use std::fs::File;
-use std::io::Error;
+use std::io::{Error, ErrorKind};
 use std::io::prelude::*;
 use std::path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Settings supplied for a single tool run, e.g. an `--out_dtype` flag used alongside `--run`.
+/// When set, these take precedence over the contents of the settings.json file.
+static RUN_CONFIGS: Mutex<Option<Configs>> = Mutex::new(None);
 
 /// A structure to hold environment settings. Backed by settings.json file in same directory
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Configs {
     pub verbose_mode: bool,
     pub working_directory: String,
     pub compress_rasters: bool,
     pub max_procs: isize,
+    /// The data type of output rasters, one of 'auto', 'native', 'u8', 'i16', 'u16', 'i32', 'f32',
+    /// or 'f64'. With 'auto', categorical integer outputs are narrowed to the smallest type that
+    /// holds their values; with 'native', outputs are written using the type chosen by the tool.
+    #[serde(default = "default_out_dtype")]
+    pub out_dtype: String,
+    /// Scale factor applied to output raster values when an explicit `out_dtype` is used, such
+    /// that stored = (value - out_offset) / out_scale.
+    #[serde(default = "default_out_scale")]
+    pub out_scale: f64,
+    #[serde(default)]
+    pub out_offset: f64,
+    /// Determines whether the statistics of output rasters are cached in `.aux.xml` sidecar files.
+    #[serde(default = "default_cache_statistics")]
+    pub cache_statistics: bool,
+    /// The device used by tools that support accelerated computation, either 'cpu' or 'gpu'.
+    /// Tools fall back to the CPU when no GPU is available.
+    #[serde(default = "default_compute")]
+    pub compute: String,
+    /// Determines whether tools refuse to replace existing output files.
+    #[serde(default)]
+    pub no_overwrite: bool,
+    /// Determines whether output GeoTIFFs are written as Cloud-Optimized GeoTIFFs, i.e. tiled,
+    /// with internal overviews, and with all of the IFDs placed ahead of the image data.
+    #[serde(default)]
+    pub cog: bool,
+    /// Determines whether a `.provenance.json` sidecar recording the tool run that created each
+    /// output file is written.
+    #[serde(default = "default_provenance")]
+    pub provenance: bool,
+    /// A JSON Lines file to which a record of every tool run is appended; empty to disable.
+    #[serde(default)]
+    pub run_manifest: String,
+    /// The layout of output Whitebox rasters (.dep/.tas): 'v1' for the original flat format,
+    /// or 'none', 'lz4', 'deflate', or 'zstd' for the tiled v2 format using that tile compression.
+    #[serde(default = "default_whitebox_raster_compression")]
+    pub whitebox_raster_compression: String,
+    /// The compression of the tiles of output GeoTIFFs, one of 'none', 'lzw', 'deflate', or
+    /// 'zstd'. Output GeoTIFFs are only compressed when `compress_rasters` is true.
+    #[serde(default = "default_geotiff_compression")]
+    pub geotiff_compression: String,
+    /// Determines whether a predictor is applied ahead of the compression of output GeoTIFFs,
+    /// i.e. horizontal differencing for integer data and the floating-point predictor for
+    /// floating-point data.
+    #[serde(default)]
+    pub geotiff_predictor: bool,
+    /// The maximum size, in megabytes, of the in-memory cache of recently read rasters, which
+    /// benefits processes that run tools repeatedly on the same inputs; 0 disables the cache.
+    #[serde(default)]
+    pub raster_cache_mb: usize,
+    /// The maximum size, in megabytes, of the cache of decoded tiles held by each tile-backed
+    /// raster. When non-zero, tools that support out-of-core processing stream Whitebox raster
+    /// inputs that are too large to fit within the cache rather than reading them into memory;
+    /// 0 disables out-of-core processing.
+    #[serde(default)]
+    pub tile_cache_mb: usize,
+    /// The maximum size, in megabytes, of the disk cache of the blocks of rasters read from URLs
+    /// and object stores with range requests, which spares repeated transfers when tools are run
+    /// on the same remote input (e.g. a cloud-optimized GeoTIFF); 0 disables the cache.
+    #[serde(default = "default_remote_cache_mb")]
+    pub remote_cache_mb: usize,
+    /// The libpq-style connection string (e.g. 'host=localhost dbname=gis user=postgres') of
+    /// the database holding PostGIS datasets, i.e. those named 'postgis:schema.table'.
+    #[serde(default)]
+    pub postgis_connection: String,
+    /// Determines whether tools that write HTML reports also write the data underlying each
+    /// report, i.e. its tables and chart data, to a .json file of the same name.
+    #[serde(default)]
+    pub report_json: bool,
+    /// The band (1-based) read from multi-dimensional NetCDF and Zarr inputs, i.e. the position
+    /// along their leading (e.g. time or depth) dimensions, with the last varying fastest.
+    #[serde(default = "default_netcdf_band")]
+    pub netcdf_band: usize,
+    /// The date (e.g. 2020-07-01) or time coordinate value of the time step read from NetCDF
+    /// inputs, in which case `netcdf_band` selects among their other leading dimensions; empty
+    /// to select the time step with `netcdf_band`.
+    #[serde(default)]
+    pub netcdf_time: String,
+    /// A shared JSON file of tool parameter presets, e.g. one maintained for a team, read along
+    /// with the user's own presets file; empty for none.
+    #[serde(default)]
+    pub presets_file: String,
+    /// The Zarr format version (2 or 3) of output Zarr stores.
+    #[serde(default = "default_zarr_version")]
+    pub zarr_version: u8,
+    /// The extension, and so the format, of output rasters whose file names have none, e.g.
+    /// 'tif' or 'dep'.
+    #[serde(default = "default_raster_format")]
+    pub default_raster_format: String,
+    /// Determines whether the threads of tools that support it, i.e. the large-memory
+    /// hydrological tools, are pinned to the processors of NUMA nodes (Linux only).
+    #[serde(default)]
+    pub numa_pinning: bool,
+}
+
+fn default_out_dtype() -> String {
+    String::from("auto")
+}
+
+fn default_out_scale() -> f64 {
+    1.0
+}
+
+fn default_cache_statistics() -> bool {
+    true
+}
+
+fn default_compute() -> String {
+    String::from("cpu")
+}
+
+fn default_provenance() -> bool {
+    true
+}
+
+fn default_whitebox_raster_compression() -> String {
+    String::from("v1")
+}
+
+fn default_geotiff_compression() -> String {
+    String::from("deflate")
+}
+
+fn default_remote_cache_mb() -> usize {
+    1024
+}
+
+fn default_netcdf_band() -> usize {
+    1
+}
+
+fn default_zarr_version() -> u8 {
+    2
+}
+
+fn default_raster_format() -> String {
+    String::from("tif")
 }
 
 impl Configs {
@@ -21,39 +183,219 @@ impl Configs {
             verbose_mode: true,
             working_directory: String::new(),
             compress_rasters: true,
-            max_procs: -1
+            max_procs: -1,
+            out_dtype: default_out_dtype(),
+            out_scale: default_out_scale(),
+            out_offset: 0.0,
+            cache_statistics: default_cache_statistics(),
+            compute: default_compute(),
+            no_overwrite: false,
+            cog: false,
+            provenance: default_provenance(),
+            run_manifest: String::new(),
+            whitebox_raster_compression: default_whitebox_raster_compression(),
+            geotiff_compression: default_geotiff_compression(),
+            geotiff_predictor: false,
+            raster_cache_mb: 0,
+            tile_cache_mb: 0,
+            remote_cache_mb: default_remote_cache_mb(),
+            postgis_connection: String::new(),
+            report_json: false,
+            netcdf_band: default_netcdf_band(),
+            netcdf_time: String::new(),
+            presets_file: String::new(),
+            zarr_version: default_zarr_version(),
+            default_raster_format: default_raster_format(),
+            numa_pinning: false,
         }
     }
 }
 
 pub fn get_configs() -> std::result::Result<Configs, Error> {
+    if let Some(configs) = RUN_CONFIGS.lock().expect("Error reading run settings.").as_ref() {
+        return Ok(configs.clone());
+    }
+    let mut settings = serde_json::to_value(read_settings_file()?)?;
+    if let Value::Object(settings) = &mut settings {
+        for o in read_overrides()? {
+            settings.insert(o.key, o.value);
+        }
+    }
+    Ok(serde_json::from_value(settings)?)
+}
+
+/// Returns the settings.json file, in the WhiteboxTools directory.
+pub fn settings_file() -> std::result::Result<PathBuf, Error> {
     let mut exe_path = std::env::current_dir()?.to_str().unwrap_or("No exe path found.").to_string();
     let plugin_dir = path::MAIN_SEPARATOR.to_string() + "plugins";
     if exe_path.ends_with(&plugin_dir) {
         exe_path = exe_path.replace(&plugin_dir, "");
     }
-    let config_file = exe_path + &path::MAIN_SEPARATOR.to_string() + "settings.json";
-    // let contents = fs::read_to_string(config_file).expect("Failed to open config_file.json file.");
-    // let configs: Configs = serde_json::from_str(&contents).expect("Failed to parse config_file.json file.");
-    let configs: Configs = match fs::read_to_string(config_file) {
-        Ok(contents) => {
-            serde_json::from_str(&contents).expect("Failed to parse config_file.json file.")
-        }, 
-        Err(_) => {
-            Configs::new()
-        }
+    Ok(PathBuf::from(exe_path).join("settings.json"))
+}
+
+/// Returns the WhiteboxTools directory of the user's configuration directory (%APPDATA% on
+/// Windows, and otherwise $XDG_CONFIG_HOME or ~/.config), whether or not it exists.
+pub fn user_config_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(|d| PathBuf::from(d).join("WhiteboxTools"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+            .map(|d| d.join("whitebox_tools"))
+    }
+}
+
+/// Returns the configuration file, i.e. the file named by the WBT_CONFIG_FILE environment
+/// variable or, if it is unset, config.json within the user's configuration directory, whether
+/// or not it exists.
+pub fn config_file() -> Option<PathBuf> {
+    match env::var_os("WBT_CONFIG_FILE") {
+        Some(f) if !f.is_empty() => Some(PathBuf::from(f)),
+        _ => user_config_dir().map(|d| d.join("config.json")),
+    }
+}
+
+/// Reads the settings.json file, returning the defaults if it doesn't exist.
+fn read_settings_file() -> std::result::Result<Configs, Error> {
+    let config_file = settings_file()?;
+    match fs::read_to_string(&config_file) {
+        Ok(contents) => serde_json::from_str(&contents).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("The settings file {} could not be read: {}", config_file.display(), e),
+            )
+        }),
+        Err(_) => Ok(Configs::new()),
+    }
+}
+
+/// A setting that is overridden by the configuration file or an environment variable.
+#[derive(Debug, Clone)]
+pub struct SettingOverride {
+    pub key: String,
+    pub value: Value,
+    /// The configuration file or environment variable that sets the value.
+    pub source: String,
+}
+
+/// Returns the settings that the configuration file and the environment variables override.
+pub fn read_overrides() -> std::result::Result<Vec<SettingOverride>, Error> {
+    let defaults = match serde_json::to_value(Configs::new())? {
+        Value::Object(map) => map,
+        _ => Map::new(),
     };
-    Ok(configs)
+    let mut overrides: Vec<SettingOverride> = vec![];
+
+    if let Some(file) = config_file() {
+        let contents = match fs::read_to_string(&file) {
+            Ok(c) => Some(c),
+            Err(ref e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        if let Some(contents) = contents {
+            let invalid = |message: String| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("The configuration file {} {}", file.display(), message),
+                )
+            };
+            let values: Map<String, Value> = serde_json::from_str(&contents)
+                .map_err(|e| invalid(format!("could not be read: {}", e)))?;
+            for (key, value) in values {
+                let default = defaults
+                    .get(&key)
+                    .ok_or_else(|| invalid(format!("sets '{}', which is not a setting.", key)))?;
+                let value = match &value {
+                    Value::String(_) if default.is_string() => Some(value.clone()),
+                    _ if default.is_string() => None,
+                    // other values may also be given as strings, as in environment variables
+                    Value::String(s) => parse_setting(s, default),
+                    _ => parse_setting(&value.to_string(), default),
+                }
+                .ok_or_else(|| invalid(format!("sets '{}' to a value of the wrong type.", key)))?;
+                overrides.push(SettingOverride { key, value, source: file.display().to_string() });
+            }
+        }
+    }
+
+    for (key, default) in &defaults {
+        let var = format!("WBT_{}", key.to_uppercase());
+        if let Ok(s) = env::var(&var) {
+            let value = parse_setting(&s, default).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The environment variable {} has an invalid value: {}", var, s),
+                )
+            })?;
+            overrides.retain(|o| o.key != *key);
+            overrides.push(SettingOverride { key: key.clone(), value, source: var });
+        }
+    }
+    // the working directory always ends with a separator, as when set by the --wd flag
+    for o in overrides.iter_mut() {
+        if let Value::String(dir) = &mut o.value {
+            if o.key == "working_directory" && !dir.is_empty() && !dir.ends_with(path::MAIN_SEPARATOR) {
+                dir.push(path::MAIN_SEPARATOR);
+            }
+        }
+    }
+    Ok(overrides)
+}
+
+/// Parses the text value of a setting, e.g. that of an environment variable, according to the
+/// type of its default value.
+fn parse_setting(s: &str, default: &Value) -> Option<Value> {
+    let s = s.trim();
+    match default {
+        Value::Bool(_) => match s.to_lowercase().as_str() {
+            "true" | "t" | "yes" | "y" | "on" | "1" => Some(Value::Bool(true)),
+            "false" | "f" | "no" | "n" | "off" | "0" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        Value::Number(n) if n.is_f64() => s.parse::<f64>().ok().map(Value::from),
+        Value::Number(n) if n.is_u64() => s.parse::<u64>().ok().map(Value::from),
+        Value::Number(_) => s.parse::<i64>().ok().map(Value::from),
+        _ => Some(Value::String(s.to_string())),
+    }
 }
 
+/// Sets the configurations used for the remainder of the current run without modifying the
+/// settings.json file.
+pub fn set_run_configs(configs: &Configs) {
+    *RUN_CONFIGS.lock().expect("Error updating run settings.") = Some(configs.clone());
+}
+
+/// Saves the settings that differ from those in effect to the settings.json file. Settings that
+/// are overridden by the configuration file or an environment variable are saved, but a warning
+/// is printed, since they won't take effect while they are overridden.
 pub fn save_configs<'a>(configs: &Configs) -> std::result::Result<(), Error> {
-    let configs_json = serde_json::to_string_pretty(&configs).expect("Error converting Configs object to JSON.");
-    let mut exe_path = std::env::current_dir()?.to_str().unwrap_or("No exe path found.").to_string();
-    let plugin_dir = path::MAIN_SEPARATOR.to_string() + "plugins";
-    if exe_path.ends_with(&plugin_dir) {
-        exe_path = exe_path.replace(&plugin_dir, "");
+    let overrides = read_overrides()?;
+    let mut current = serde_json::to_value(read_settings_file()?)?;
+    let mut saved = current.clone();
+    if let Value::Object(current) = &mut current {
+        for o in &overrides {
+            current.insert(o.key.clone(), o.value.clone());
+        }
     }
-    let config_file = exe_path + &path::MAIN_SEPARATOR.to_string() + "settings.json";
+    if let (Value::Object(new), Value::Object(current), Value::Object(saved)) =
+        (serde_json::to_value(configs)?, &current, &mut saved)
+    {
+        for (key, value) in new {
+            if current.get(&key) != Some(&value) {
+                if let Some(o) = overrides.iter().find(|o| o.key == key) {
+                    eprintln!(
+                        "Warning: The {} setting is saved to settings.json, but it is overridden by {}.",
+                        key, o.source
+                    );
+                }
+                saved.insert(key, value);
+            }
+        }
+    }
+    let configs_json = serde_json::to_string_pretty(&saved).expect("Error converting Configs object to JSON.");
+    let config_file = settings_file()?;
     match File::create(config_file) {
         Ok(mut file) => {
             match file.write_all(configs_json.as_bytes()) {
@@ -71,3 +413,20 @@ pub fn save_configs<'a>(configs: &Configs) -> std::result::Result<(), Error> {
     Ok(())
 }
 
+#[cfg(test)]
+mod test {
+    use super::parse_setting;
+    use serde_json::Value;
+
+    #[test]
+    fn test_parse_setting() {
+        assert_eq!(parse_setting("Yes", &Value::Bool(false)), Some(Value::Bool(true)));
+        assert_eq!(parse_setting("0", &Value::Bool(true)), Some(Value::Bool(false)));
+        assert_eq!(parse_setting("maybe", &Value::Bool(true)), None);
+        assert_eq!(parse_setting(" 4 ", &Value::from(-1i64)), Some(Value::from(4i64)));
+        assert_eq!(parse_setting("-1", &Value::from(0u64)), None);
+        assert_eq!(parse_setting("2048", &Value::from(0u64)), Some(Value::from(2048u64)));
+        assert_eq!(parse_setting("0.01", &Value::from(1.0)), Some(Value::from(0.01)));
+        assert_eq!(parse_setting("zstd", &Value::from("deflate")), Some(Value::from("zstd")));
+    }
+}
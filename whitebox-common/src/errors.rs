@@ -0,0 +1,194 @@
+/*
+NOTES: Tools and the libraries that they use return std::io::Error, and so the cause of a
+failure is carried within the io::Error, rather than by a separate error type that every
+function would have to return. An error created with `ErrorCause::error` carries its cause
+explicitly, and failure sites whose cause matters, e.g. the check that input grids match,
+must create their errors this way. Any other error, e.g. one returned by the standard library,
+takes the cause of its io::ErrorKind; its message is never consulted. Panics are 'other'.
+
+The command-line program exits with the code of the cause of a failure, and reports it in the
+JSON payloads of the --json and --progress=json modes, so that scripted pipelines can branch
+on why a tool failed:
+
+    0  success
+    1  other
+    2  invalid_parameter
+    3  file_not_found
+    4  unsupported_format
+    5  grid_mismatch
+    6  out_of_memory
+    7  cancelled
+    8  license
+
+Exit codes are part of the program's interface; new causes must take new codes.
+*/
+
+use serde_json::{json, Value};
+use std::error;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+
+/// The cause of a failed tool run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCause {
+    Other,
+    InvalidParameter,
+    FileNotFound,
+    UnsupportedFormat,
+    GridMismatch,
+    OutOfMemory,
+    Cancelled,
+    License,
+}
+
+const CAUSES: [ErrorCause; 8] = [
+    ErrorCause::Other,
+    ErrorCause::InvalidParameter,
+    ErrorCause::FileNotFound,
+    ErrorCause::UnsupportedFormat,
+    ErrorCause::GridMismatch,
+    ErrorCause::OutOfMemory,
+    ErrorCause::Cancelled,
+    ErrorCause::License,
+];
+
+impl ErrorCause {
+    /// Returns an error with this cause.
+    pub fn error<S: Into<String>>(self, message: S) -> Error {
+        Error::new(
+            self.io_kind(),
+            CausedError {
+                cause: self,
+                message: message.into(),
+            },
+        )
+    }
+
+    /// Returns the cause of an error, or the cause of its kind if the error wasn't created with
+    /// a cause.
+    pub fn of(e: &Error) -> ErrorCause {
+        if let Some(caused) = e
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<CausedError>())
+        {
+            return caused.cause;
+        }
+        match e.kind() {
+            ErrorKind::Interrupted => ErrorCause::Cancelled,
+            ErrorKind::NotFound => ErrorCause::FileNotFound,
+            ErrorKind::OutOfMemory => ErrorCause::OutOfMemory,
+            ErrorKind::InvalidInput => ErrorCause::InvalidParameter,
+            ErrorKind::InvalidData | ErrorKind::UnexpectedEof => ErrorCause::UnsupportedFormat,
+            _ => ErrorCause::Other,
+        }
+    }
+
+    /// Returns the cause with the given process exit code, if any.
+    pub fn from_exit_code(code: i32) -> Option<ErrorCause> {
+        CAUSES.iter().copied().find(|c| c.exit_code() == code)
+    }
+
+    /// The exit code of the command-line program when a run fails with this cause.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorCause::Other => 1,
+            ErrorCause::InvalidParameter => 2,
+            ErrorCause::FileNotFound => 3,
+            ErrorCause::UnsupportedFormat => 4,
+            ErrorCause::GridMismatch => 5,
+            ErrorCause::OutOfMemory => 6,
+            ErrorCause::Cancelled => 7,
+            ErrorCause::License => 8,
+        }
+    }
+
+    /// The name of the cause used in JSON payloads, e.g. 'grid_mismatch'.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorCause::Other => "other",
+            ErrorCause::InvalidParameter => "invalid_parameter",
+            ErrorCause::FileNotFound => "file_not_found",
+            ErrorCause::UnsupportedFormat => "unsupported_format",
+            ErrorCause::GridMismatch => "grid_mismatch",
+            ErrorCause::OutOfMemory => "out_of_memory",
+            ErrorCause::Cancelled => "cancelled",
+            ErrorCause::License => "license",
+        }
+    }
+
+    fn io_kind(&self) -> ErrorKind {
+        match self {
+            ErrorCause::Other => ErrorKind::Other,
+            ErrorCause::InvalidParameter | ErrorCause::GridMismatch => ErrorKind::InvalidInput,
+            ErrorCause::FileNotFound => ErrorKind::NotFound,
+            ErrorCause::UnsupportedFormat => ErrorKind::InvalidData,
+            ErrorCause::OutOfMemory => ErrorKind::OutOfMemory,
+            ErrorCause::Cancelled => ErrorKind::Interrupted,
+            ErrorCause::License => ErrorKind::PermissionDenied,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCause {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Returns the JSON payload describing a failure, i.e. its cause, exit code, and message.
+pub fn error_json(cause: ErrorCause, message: &str) -> Value {
+    json!({
+        "kind": cause.name(),
+        "exit_code": cause.exit_code(),
+        "message": message,
+    })
+}
+
+/// The inner error of an io::Error created with a cause.
+#[derive(Debug)]
+struct CausedError {
+    cause: ErrorCause,
+    message: String,
+}
+
+impl fmt::Display for CausedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl error::Error for CausedError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_error_cause() {
+        let e = ErrorCause::GridMismatch.error("The grids differ.");
+        assert_eq!(ErrorCause::of(&e), ErrorCause::GridMismatch);
+        assert_eq!(e.kind(), ErrorKind::InvalidInput);
+        assert_eq!(e.to_string(), "The grids differ.");
+
+        // the message of an error without a cause is never consulted
+        let e = Error::new(
+            ErrorKind::InvalidInput,
+            "The input files must have the same number of rows and columns and spatial extent.",
+        );
+        assert_eq!(ErrorCause::of(&e), ErrorCause::InvalidParameter);
+        let e = Error::new(ErrorKind::Other, "Unsupported point format");
+        assert_eq!(ErrorCause::of(&e), ErrorCause::Other);
+        let e = Error::new(ErrorKind::NotFound, "dem.tif");
+        assert_eq!(ErrorCause::of(&e), ErrorCause::FileNotFound);
+        let e = Error::new(ErrorKind::Interrupted, "The tool was cancelled.");
+        assert_eq!(ErrorCause::of(&e), ErrorCause::Cancelled);
+        let e = ErrorCause::License.error("The license is missing.");
+        assert_eq!(ErrorCause::of(&e), ErrorCause::License);
+        assert_eq!(e.kind(), ErrorKind::PermissionDenied);
+
+        for cause in CAUSES.iter() {
+            assert_eq!(ErrorCause::from_exit_code(cause.exit_code()), Some(*cause));
+        }
+        assert_eq!(ErrorCause::from_exit_code(0), None);
+    }
+}
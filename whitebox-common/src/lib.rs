@@ -1,5 +1,6 @@
 pub mod algorithms;
 pub mod configs;
+pub mod errors;
 pub mod plugins;
 pub mod rendering;
 pub mod spatial_ref_system;
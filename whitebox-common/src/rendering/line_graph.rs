@@ -720,12 +720,13 @@ impl LineGraph {
         cm.appendChild(list);
         document.getElementById(plot.parentId).appendChild(cm);
 
-        var menu = document.getElementById('context-menu');
-        document.onclick = function () {
+        // refer to this chart's own menu and plot, since a report may hold several charts
+        var menu = cm;
+        document.addEventListener('click', function () {
             menu.style.display = 'none';
-        };
+        });
 
-        document.getElementById('plotSvg').oncontextmenu = function (evt) {
+        svg.oncontextmenu = function (evt) {
             evt = (evt) ? evt : ((event) ? event : null);
             var posnX = (evt.pageX) ? evt.pageX : ((evt.offsetX) ? evt.offsetX + 10 : null);
             var posnY = (evt.pageY) ? evt.pageY : ((evt.offsetY) ? evt.offsetY + 10 : null);
@@ -2,11 +2,13 @@
 mod box_whisker;
 mod histogram;
 mod line_graph;
+mod report;
 mod scattergram;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::box_whisker::BoxAndWhiskerPlot;
 pub use self::histogram::Histogram;
 pub use self::line_graph::LineGraph;
+pub use self::report::{Report, ReportValue};
 pub use self::scattergram::Scattergram;
 pub mod html;
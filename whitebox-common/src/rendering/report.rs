@@ -0,0 +1,454 @@
+/////////////////////////////////////////
+// HTML reports of tool results        //
+/////////////////////////////////////////
+
+use super::html::{escape_html, get_css};
+use super::{Histogram, LineGraph, Scattergram};
+use crate::configs::get_configs;
+use crate::utils::write_atomically;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+
+/// A value displayed in a report, i.e. a property or a table cell.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReportValue {
+    Text(String),
+    Integer(i64),
+    /// A real number, displayed with the given number of decimal places.
+    Real(f64, usize),
+}
+
+impl ReportValue {
+    fn to_html(&self) -> String {
+        match self {
+            ReportValue::Text(s) => escape_html(s),
+            ReportValue::Integer(v) => v.to_string(),
+            ReportValue::Real(v, decimals) => format!("{:.*}", decimals, v),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            ReportValue::Text(s) => json!(s),
+            ReportValue::Integer(v) => json!(v),
+            // JSON has no representation of NaN or infinity
+            ReportValue::Real(v, _) if !v.is_finite() => Value::Null,
+            ReportValue::Real(v, _) => json!(v),
+        }
+    }
+}
+
+impl From<&str> for ReportValue {
+    fn from(s: &str) -> ReportValue {
+        ReportValue::Text(s.to_string())
+    }
+}
+
+impl From<String> for ReportValue {
+    fn from(s: String) -> ReportValue {
+        ReportValue::Text(s)
+    }
+}
+
+impl From<i64> for ReportValue {
+    fn from(v: i64) -> ReportValue {
+        ReportValue::Integer(v)
+    }
+}
+
+impl From<usize> for ReportValue {
+    fn from(v: usize) -> ReportValue {
+        ReportValue::Integer(v as i64)
+    }
+}
+
+enum ReportItem {
+    Heading(String),
+    /// A paragraph of HTML text.
+    Paragraph(String),
+    Property(String, ReportValue),
+    Table {
+        caption: String,
+        columns: Vec<String>,
+        rows: Vec<Vec<ReportValue>>,
+    },
+    Histogram(Histogram),
+    LineGraph(LineGraph),
+    Scattergram(Scattergram),
+}
+
+/// A self-contained HTML report of the results of a tool, built from headings, paragraphs,
+/// properties (e.g. the input file or a summary statistic), tables, and interactive charts,
+/// and laid out with the style sheet shared by all reports.
+///
+/// The data underlying the report, i.e. its properties, tables, and the series of its charts,
+/// can also be written to a JSON file, alongside the HTML file, when the `report_json` setting
+/// is enabled.
+///
+/// ```ignore
+/// let mut report = Report::new("Histogram Analysis");
+/// report.add_property("Image", input.get_short_filename());
+/// report.add_histogram(Histogram { ... });
+/// report.write(&output_file)?;
+/// ```
+pub struct Report {
+    title: String,
+    items: Vec<ReportItem>,
+}
+
+impl Report {
+    pub fn new(title: &str) -> Report {
+        Report {
+            title: title.to_string(),
+            items: vec![],
+        }
+    }
+
+    pub fn add_heading(&mut self, text: &str) {
+        self.items.push(ReportItem::Heading(text.to_string()));
+    }
+
+    /// Adds a paragraph, e.g. of notes; `html` may contain markup and is not escaped.
+    pub fn add_paragraph(&mut self, html: &str) {
+        self.items.push(ReportItem::Paragraph(html.to_string()));
+    }
+
+    pub fn add_property<V: Into<ReportValue>>(&mut self, label: &str, value: V) {
+        self.items
+            .push(ReportItem::Property(label.to_string(), value.into()));
+    }
+
+    pub fn add_table(&mut self, caption: &str, columns: &[&str], rows: Vec<Vec<ReportValue>>) {
+        self.items.push(ReportItem::Table {
+            caption: caption.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows,
+        });
+    }
+
+    /// Adds a histogram; its `parent_id` is replaced by an identifier unique within the report.
+    pub fn add_histogram(&mut self, mut histogram: Histogram) {
+        histogram.parent_id = self.next_chart_id();
+        self.items.push(ReportItem::Histogram(histogram));
+    }
+
+    /// Adds a line graph, e.g. of profiles; its `parent_id` is replaced by an identifier unique
+    /// within the report.
+    pub fn add_line_graph(&mut self, mut graph: LineGraph) {
+        graph.parent_id = self.next_chart_id();
+        self.items.push(ReportItem::LineGraph(graph));
+    }
+
+    /// Adds a scatterplot; its `parent_id` is replaced by an identifier unique within the report.
+    pub fn add_scattergram(&mut self, mut graph: Scattergram) {
+        graph.parent_id = self.next_chart_id();
+        self.items.push(ReportItem::Scattergram(graph));
+    }
+
+    fn next_chart_id(&self) -> String {
+        let num_charts = self
+            .items
+            .iter()
+            .filter(|item| {
+                matches!(
+                    item,
+                    ReportItem::Histogram(_)
+                        | ReportItem::LineGraph(_)
+                        | ReportItem::Scattergram(_)
+                )
+            })
+            .count();
+        format!("chart{}", num_charts + 1)
+    }
+
+    /// Returns the report as an HTML document.
+    pub fn get_html(&self) -> String {
+        let mut s = format!(
+            "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+    <meta charset=\"UTF-8\">
+    <title>{}</title>
+    {}
+    <style type=\"text/css\">
+        table {{
+            margin-left: auto;
+            margin-right: auto;
+            margin-bottom: 20px;
+        }}
+        .chart {{
+            text-align: center;
+        }}
+    </style>
+</head>
+<body>
+    <h1>{}</h1>
+",
+            escape_html(&self.title),
+            get_css(),
+            escape_html(&self.title)
+        );
+        for item in &self.items {
+            match item {
+                ReportItem::Heading(text) => {
+                    s.push_str(&format!("    <h2>{}</h2>\n", escape_html(text)));
+                }
+                ReportItem::Paragraph(html) => {
+                    s.push_str(&format!("    <p>{}</p>\n", html));
+                }
+                ReportItem::Property(label, value) => {
+                    s.push_str(&format!(
+                        "    <p><strong>{}</strong>: {}</p>\n",
+                        escape_html(label),
+                        value.to_html()
+                    ));
+                }
+                ReportItem::Table {
+                    caption,
+                    columns,
+                    rows,
+                } => {
+                    s.push_str("    <table>\n");
+                    if !caption.is_empty() {
+                        s.push_str(&format!(
+                            "        <caption>{}</caption>\n",
+                            escape_html(caption)
+                        ));
+                    }
+                    s.push_str("        <tr>");
+                    for column in columns {
+                        s.push_str(&format!(
+                            "<th class=\"header\">{}</th>",
+                            escape_html(column)
+                        ));
+                    }
+                    s.push_str("</tr>\n");
+                    for row in rows {
+                        s.push_str("        <tr>");
+                        for value in row {
+                            match value {
+                                ReportValue::Text(_) => {
+                                    s.push_str(&format!("<td>{}</td>", value.to_html()))
+                                }
+                                _ => s.push_str(&format!(
+                                    "<td class=\"numberCell\">{}</td>",
+                                    value.to_html()
+                                )),
+                            }
+                        }
+                        s.push_str("</tr>\n");
+                    }
+                    s.push_str("    </table>\n");
+                }
+                ReportItem::Histogram(chart) => {
+                    s.push_str(&chart_div(&chart.parent_id, &chart.get_svg()));
+                }
+                ReportItem::LineGraph(chart) => {
+                    s.push_str(&chart_div(&chart.parent_id, &chart.get_svg()));
+                }
+                ReportItem::Scattergram(chart) => {
+                    s.push_str(&chart_div(&chart.parent_id, &chart.get_svg()));
+                }
+            }
+        }
+        s.push_str("</body>\n</html>\n");
+        s
+    }
+
+    /// Returns the data underlying the report as a JSON document, holding its title, its
+    /// properties, its tables, and the data of its charts.
+    pub fn get_json(&self) -> String {
+        let mut properties = Map::new();
+        let mut tables = vec![];
+        let mut charts = vec![];
+        for item in &self.items {
+            match item {
+                ReportItem::Heading(_) | ReportItem::Paragraph(_) => {}
+                ReportItem::Property(label, value) => {
+                    properties.insert(label.clone(), value.to_json());
+                }
+                ReportItem::Table {
+                    caption,
+                    columns,
+                    rows,
+                } => {
+                    let rows: Vec<Value> = rows
+                        .iter()
+                        .map(|row| Value::Array(row.iter().map(|v| v.to_json()).collect()))
+                        .collect();
+                    tables.push(json!({
+                        "caption": caption,
+                        "columns": columns,
+                        "rows": rows,
+                    }));
+                }
+                ReportItem::Histogram(chart) => {
+                    charts.push(json!({
+                        "type": "histogram",
+                        "x_axis_label": chart.x_axis_label,
+                        "min_bin_value": finite_or_null(chart.min_bin_val),
+                        "bin_width": finite_or_null(chart.bin_width),
+                        "frequencies": chart.freq_data,
+                    }));
+                }
+                ReportItem::LineGraph(chart) => {
+                    charts.push(json!({
+                        "type": "line_graph",
+                        "x_axis_label": chart.x_axis_label,
+                        "y_axis_label": chart.y_axis_label,
+                        "series": series_json(&chart.series_labels, &chart.data_x, &chart.data_y),
+                    }));
+                }
+                ReportItem::Scattergram(chart) => {
+                    charts.push(json!({
+                        "type": "scattergram",
+                        "x_axis_label": chart.x_axis_label,
+                        "y_axis_label": chart.y_axis_label,
+                        "series": series_json(&chart.series_labels, &chart.data_x, &chart.data_y),
+                    }));
+                }
+            }
+        }
+        let report = json!({
+            "title": self.title,
+            "properties": properties,
+            "tables": tables,
+            "charts": charts,
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+
+    /// Writes the report to an HTML file and, if the `report_json` setting is enabled, its data
+    /// to a JSON file of the same name (e.g. `histo.json` for `histo.html`).
+    pub fn write(&self, file_name: &str) -> Result<(), Error> {
+        write_atomically(file_name, |temp_file| fs::write(temp_file, self.get_html()))?;
+        if get_configs()?.report_json {
+            let json_file = Path::new(file_name).with_extension("json");
+            write_atomically(&json_file.to_string_lossy(), |temp_file| {
+                fs::write(temp_file, self.get_json())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Places a chart within its own element, running its script within its own function scope,
+/// such that the variables of the charts of a report do not collide.
+fn chart_div(id: &str, svg: &str) -> String {
+    let script = svg
+        .replacen("<script>", "<script>\n(function() {", 1)
+        .replace("</script>", "})();\n</script>");
+    format!("    <div id=\"{}\" class=\"chart\">{}</div>\n", id, script)
+}
+
+fn finite_or_null(v: f64) -> Value {
+    if v.is_finite() {
+        json!(v)
+    } else {
+        Value::Null
+    }
+}
+
+fn series_json(labels: &[String], data_x: &[Vec<f64>], data_y: &[Vec<f64>]) -> Vec<Value> {
+    data_y
+        .iter()
+        .enumerate()
+        .map(|(i, y)| {
+            let x: Vec<Value> = match data_x.get(i) {
+                Some(x) => x.iter().map(|v| finite_or_null(*v)).collect(),
+                // series without x values are plotted against 1, 2, 3, ...
+                None => (1..=y.len()).map(|v| json!(v)).collect(),
+            };
+            json!({
+                "label": labels.get(i).cloned().unwrap_or_default(),
+                "x": x,
+                "y": y.iter().map(|v| finite_or_null(*v)).collect::<Vec<Value>>(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Report, ReportValue};
+    use crate::rendering::{Histogram, LineGraph};
+    use serde_json::Value;
+
+    fn histogram() -> Histogram {
+        Histogram {
+            parent_id: "histo".to_string(),
+            width: 700f64,
+            height: 500f64,
+            freq_data: vec![1, 4, 2],
+            min_bin_val: 10f64,
+            bin_width: 0.5,
+            x_axis_label: "Value".to_string(),
+            cumulative: false,
+        }
+    }
+
+    #[test]
+    fn test_html_escapes_text() {
+        let mut report = Report::new("Kappa <Index>");
+        report.add_property("Classification Data", "a&b.tif");
+        report.add_table(
+            "Class Statistics",
+            &["Class", "Accuracy (%)"],
+            vec![vec!["<1>".into(), ReportValue::Real(95.123, 2)]],
+        );
+        let html = report.get_html();
+        assert!(html.contains("<title>Kappa &lt;Index&gt;</title>"));
+        assert!(html.contains("<strong>Classification Data</strong>: a&amp;b.tif"));
+        assert!(html.contains("<td>&lt;1&gt;</td><td class=\"numberCell\">95.12</td>"));
+    }
+
+    #[test]
+    fn test_charts_have_unique_ids_and_scopes() {
+        let mut report = Report::new("Charts");
+        report.add_histogram(histogram());
+        report.add_line_graph(LineGraph {
+            parent_id: "graph".to_string(),
+            width: 700f64,
+            height: 500f64,
+            data_x: vec![vec![0.0, 1.0]],
+            data_y: vec![vec![5.0, 6.0]],
+            series_labels: vec!["Profile 1".to_string()],
+            x_axis_label: "Distance".to_string(),
+            y_axis_label: "Elevation".to_string(),
+            draw_points: false,
+            draw_gridlines: true,
+            draw_legend: false,
+            draw_grey_background: false,
+        });
+        let html = report.get_html();
+        assert!(html.contains("<div id=\"chart1\" class=\"chart\">"));
+        assert!(html.contains("<div id=\"chart2\" class=\"chart\">"));
+        assert!(html.contains("parentId = \"chart1\""));
+        assert_eq!(html.matches("(function() {").count(), 2);
+        assert_eq!(html.matches("})();\n</script>").count(), 2);
+    }
+
+    #[test]
+    fn test_json_holds_data() {
+        let mut report = Report::new("Histogram Analysis");
+        report.add_property("Image", "dem.tif");
+        report.add_property("Kappa", ReportValue::Real(f64::NAN, 3));
+        report.add_paragraph("Notes are not data.");
+        report.add_table(
+            "Totals",
+            &["Class", "N"],
+            vec![vec!["1".into(), 12usize.into()]],
+        );
+        report.add_histogram(histogram());
+        let json: Value = serde_json::from_str(&report.get_json()).unwrap();
+        assert_eq!(json["title"], "Histogram Analysis");
+        assert_eq!(json["properties"]["Image"], "dem.tif");
+        assert!(json["properties"]["Kappa"].is_null());
+        assert_eq!(json["tables"][0]["rows"][0][1], 12);
+        assert_eq!(json["charts"][0]["type"], "histogram");
+        assert_eq!(json["charts"][0]["bin_width"], 0.5);
+        assert_eq!(json["charts"][0]["frequencies"][1], 4);
+    }
+}
@@ -0,0 +1,363 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: A CoordinateReferenceSystem describes the coordinate reference system of a dataset by
+its EPSG code and WKT definition, either of which may be missing, and its horizontal units. It
+is a description of a dataset's CRS, used to propagate the CRS from inputs to outputs and to
+check that the inputs of multi-input tools agree, whereas the `Crs` of the projection module is
+a CRS that coordinates can be transformed to and from. The WKT definition may be of either the
+WKT1 (e.g. Esri .prj files) or WKT2 (ISO 19162) form, and a WKT2 definition can be generated for
+the CRSs that `Crs` supports.
+*/
+
+use super::{esri_wkt_from_epsg, Crs};
+use std::fmt;
+
+/// The horizontal units of a coordinate reference system.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CrsUnits {
+    Unknown,
+    Metres,
+    Feet,
+    UsSurveyFeet,
+    Degrees,
+}
+
+impl CrsUnits {
+    /// Identifies units from their name, e.g. 'metre', 'Meter', 'Foot_US', or 'degree'.
+    pub fn from_name(name: &str) -> CrsUnits {
+        let name = name.trim().to_lowercase().replace('_', " ");
+        if name.starts_with("met") || name == "m" {
+            CrsUnits::Metres
+        } else if name.contains("us") && (name.contains("foot") || name.contains("feet")) {
+            CrsUnits::UsSurveyFeet
+        } else if name.contains("foot") || name.contains("feet") || name == "ft" {
+            CrsUnits::Feet
+        } else if name.starts_with("deg") {
+            CrsUnits::Degrees
+        } else {
+            CrsUnits::Unknown
+        }
+    }
+
+    /// The name of the units, as stored in the xy_units of a raster.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrsUnits::Unknown => "not specified",
+            CrsUnits::Metres => "metres",
+            CrsUnits::Feet => "feet",
+            CrsUnits::UsSurveyFeet => "US survey feet",
+            CrsUnits::Degrees => "degrees",
+        }
+    }
+}
+
+/// The coordinate reference system of a dataset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoordinateReferenceSystem {
+    /// The EPSG code, or 0 if it is unknown.
+    pub epsg_code: u16,
+    /// The WKT definition (WKT1 or WKT2), or an empty string if it is unknown.
+    pub wkt: String,
+    pub units: CrsUnits,
+}
+
+impl Default for CoordinateReferenceSystem {
+    fn default() -> CoordinateReferenceSystem {
+        CoordinateReferenceSystem {
+            epsg_code: 0,
+            wkt: String::new(),
+            units: CrsUnits::Unknown,
+        }
+    }
+}
+
+impl CoordinateReferenceSystem {
+    /// Creates a CRS from an EPSG code and WKT definition, either of which may be missing (0 or
+    /// empty). The EPSG code is read from the WKT if it isn't given, and the units are those of
+    /// the WKT or, failing that, of `units`, e.g. the xy_units of a raster.
+    pub fn new(epsg_code: u16, wkt: &str, units: &str) -> CoordinateReferenceSystem {
+        let wkt = wkt.trim();
+        let wkt = if wkt.contains('[') {
+            wkt.to_string()
+        } else {
+            String::new()
+        };
+        let mut crs = CoordinateReferenceSystem {
+            epsg_code: if epsg_code > 0 {
+                epsg_code
+            } else {
+                epsg_from_wkt(&wkt)
+            },
+            units: units_from_wkt(&wkt),
+            wkt,
+        };
+        if crs.units == CrsUnits::Unknown {
+            crs.units = CrsUnits::from_name(units);
+        }
+        if crs.units == CrsUnits::Unknown {
+            crs.units = match Crs::from_epsg(crs.epsg_code) {
+                Some(Crs::Geographic) => CrsUnits::Degrees,
+                Some(_) => CrsUnits::Metres,
+                None => CrsUnits::Unknown,
+            };
+        }
+        crs
+    }
+
+    /// Creates a CRS from an EPSG code, with the WKT definition of the code if it is known.
+    pub fn from_epsg(epsg_code: u16) -> CoordinateReferenceSystem {
+        CoordinateReferenceSystem::new(epsg_code, &esri_wkt_from_epsg(epsg_code), "")
+    }
+
+    /// Creates a CRS from a WKT definition, e.g. the contents of a .prj file.
+    pub fn from_wkt(wkt: &str) -> CoordinateReferenceSystem {
+        CoordinateReferenceSystem::new(0, wkt, "")
+    }
+
+    /// Returns `true` if the CRS is known, i.e. it has an EPSG code or a WKT definition.
+    pub fn is_known(&self) -> bool {
+        self.epsg_code > 0 || !self.wkt.is_empty()
+    }
+
+    /// Returns whether this and `other` are the same CRS, or `None` if this can't be
+    /// determined because either is unknown. CRSs with EPSG codes are compared by their codes
+    /// and otherwise by the systems that their WKT definitions describe.
+    pub fn matches(&self, other: &CoordinateReferenceSystem) -> Option<bool> {
+        if !self.is_known() || !other.is_known() {
+            return None;
+        }
+        if self.epsg_code > 0 && other.epsg_code > 0 {
+            return Some(self.epsg_code == other.epsg_code);
+        }
+        if normalized(&self.wkt) == normalized(&other.wkt) {
+            return Some(true);
+        }
+        match (self.projection(), other.projection()) {
+            (Some(a), Some(b)) => Some(a == b),
+            _ if self.units != CrsUnits::Unknown
+                && other.units != CrsUnits::Unknown
+                && self.units != other.units =>
+            {
+                Some(false)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the CRS as one that coordinates can be transformed to and from, if it is supported.
+    pub fn projection(&self) -> Option<Crs> {
+        Crs::from_definition(self.epsg_code, &self.wkt, self.units == CrsUnits::Degrees)
+    }
+
+    /// Returns `true` if the WKT definition is of the WKT2 form.
+    pub fn is_wkt2(&self) -> bool {
+        let wkt = self.wkt.to_uppercase();
+        [
+            "PROJCRS[",
+            "GEOGCRS[",
+            "GEODCRS[",
+            "COMPOUNDCRS[",
+            "BOUNDCRS[",
+            "VERTCRS[",
+            "ENGCRS[",
+        ]
+        .iter()
+        .any(|k| wkt.starts_with(k))
+    }
+
+    /// Returns the WKT2 definition of the CRS, i.e. its own WKT if that is WKT2 and otherwise one
+    /// generated for its EPSG code, if the code is a supported geographic, Web Mercator, or
+    /// WGS84 UTM system.
+    pub fn wkt2(&self) -> Option<String> {
+        if self.is_wkt2() {
+            return Some(self.wkt.clone());
+        }
+        match Crs::from_epsg(self.epsg_code) {
+            Some(crs) if crs.epsg_code() == self.epsg_code => Some(wkt2_of(crs)),
+            _ => None,
+        }
+    }
+
+    /// A short name of the CRS, e.g. 'EPSG:32617', or the name within its WKT definition.
+    pub fn name(&self) -> String {
+        if self.epsg_code > 0 {
+            return format!("EPSG:{}", self.epsg_code);
+        }
+        let name = self.wkt.split('"').nth(1).unwrap_or("").trim();
+        if name.is_empty() {
+            "unknown".to_string()
+        } else {
+            name.to_string()
+        }
+    }
+}
+
+impl fmt::Display for CoordinateReferenceSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+fn normalized(wkt: &str) -> String {
+    wkt.chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Reads the EPSG code of the CRS from its WKT definition. The identifier of the CRS itself,
+/// i.e. an AUTHORITY (WKT1) or ID (WKT2) of the outermost element, is the last in the definition.
+fn epsg_from_wkt(wkt: &str) -> u16 {
+    let compact: String = wkt.chars().filter(|c| !c.is_whitespace()).collect();
+    let upper = compact.to_uppercase();
+    if !upper.ends_with("]]") {
+        return 0;
+    }
+    let start = match (
+        upper.rfind("AUTHORITY[\"EPSG\","),
+        upper.rfind("ID[\"EPSG\","),
+    ) {
+        (Some(a), Some(i)) => a.max(i),
+        (Some(a), None) => a,
+        (None, Some(i)) => i,
+        (None, None) => return 0,
+    };
+    let code: String = compact[start..]
+        .split(',')
+        .nth(1)
+        .unwrap_or("")
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect();
+    // the identifier must close the outermost element
+    if compact[start..].matches(']').count() != 2 {
+        return 0;
+    }
+    code.parse::<u16>().unwrap_or(0)
+}
+
+/// Reads the horizontal units from a WKT definition. These are given by the last unit of the
+/// definition, i.e. the UNIT of a WKT1 PROJCS or GEOGCS, or the unit of the axes of a WKT2 CRS.
+fn units_from_wkt(wkt: &str) -> CrsUnits {
+    let upper = wkt.to_uppercase();
+    match upper.rfind("UNIT[\"") {
+        Some(i) => {
+            let name = wkt[i + 6..].split('"').next().unwrap_or("");
+            CrsUnits::from_name(name)
+        }
+        None => CrsUnits::Unknown,
+    }
+}
+
+fn wkt2_of(crs: Crs) -> String {
+    let degree = "ANGLEUNIT[\"degree\",0.0174532925199433]";
+    let metre = "LENGTHUNIT[\"metre\",1]";
+    let datum = format!(
+        "DATUM[\"World Geodetic System 1984\",ELLIPSOID[\"WGS 84\",6378137,298.257223563,{}]],PRIMEM[\"Greenwich\",0,{}]",
+        metre, degree
+    );
+    let projected = |name: &str,
+                     method: &str,
+                     method_id: u16,
+                     parameters: Vec<(&str, f64, &str)>| {
+        let parameters = parameters
+            .iter()
+            .map(|(name, value, unit)| format!("PARAMETER[\"{}\",{},{}]", name, value, unit))
+            .collect::<Vec<String>>()
+            .join(",");
+        format!(
+            "PROJCRS[\"WGS 84 / {0}\",BASEGEOGCRS[\"WGS 84\",{1}],CONVERSION[\"{0}\",METHOD[\"{2}\",ID[\"EPSG\",{3}]],{4}],CS[Cartesian,2],AXIS[\"easting (E)\",east,ORDER[1],{5}],AXIS[\"northing (N)\",north,ORDER[2],{5}],ID[\"EPSG\",{6}]]",
+            name, datum, method, method_id, parameters, metre, crs.epsg_code()
+        )
+    };
+    match crs {
+        Crs::Geographic => format!(
+            "GEOGCRS[\"WGS 84\",{0},CS[ellipsoidal,2],AXIS[\"geodetic latitude (Lat)\",north,ORDER[1],{1}],AXIS[\"geodetic longitude (Lon)\",east,ORDER[2],{1}],ID[\"EPSG\",4326]]",
+            datum, degree
+        ),
+        Crs::WebMercator => projected(
+            "Pseudo-Mercator",
+            "Popular Visualisation Pseudo Mercator",
+            1024,
+            vec![
+                ("Latitude of natural origin", 0.0, degree),
+                ("Longitude of natural origin", 0.0, degree),
+                ("False easting", 0.0, metre),
+                ("False northing", 0.0, metre),
+            ],
+        ),
+        Crs::Utm { zone, south } => projected(
+            &format!("UTM zone {}{}", zone, if south { "S" } else { "N" }),
+            "Transverse Mercator",
+            9807,
+            vec![
+                ("Latitude of natural origin", 0.0, degree),
+                ("Longitude of natural origin", (zone * 6 - 183) as f64, degree),
+                ("Scale factor at natural origin", 0.9996, "SCALEUNIT[\"unity\",1]"),
+                ("False easting", 500000.0, metre),
+                ("False northing", if south { 10000000.0 } else { 0.0 }, metre),
+            ],
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CoordinateReferenceSystem, CrsUnits};
+
+    #[test]
+    fn test_coordinate_reference_system() {
+        let wkt = "PROJCS[\"NAD83 / UTM zone 17N\",GEOGCS[\"NAD83\",DATUM[\"North_American_Datum_1983\",SPHEROID[\"GRS 1980\",6378137,298.257222101,AUTHORITY[\"EPSG\",\"7019\"]],AUTHORITY[\"EPSG\",\"6269\"]],PRIMEM[\"Greenwich\",0],UNIT[\"degree\",0.0174532925199433],AUTHORITY[\"EPSG\",\"4269\"]],PROJECTION[\"Transverse_Mercator\"],PARAMETER[\"central_meridian\",-81],UNIT[\"metre\",1,AUTHORITY[\"EPSG\",\"9001\"]],AUTHORITY[\"EPSG\",\"26917\"]]";
+        let crs = CoordinateReferenceSystem::from_wkt(wkt);
+        assert_eq!(crs.epsg_code, 26917);
+        assert_eq!(crs.units, CrsUnits::Metres);
+        assert_eq!(crs.name(), "EPSG:26917");
+        assert!(!crs.is_wkt2());
+
+        // an Esri WKT without an authority
+        let esri = CoordinateReferenceSystem::from_wkt(
+            "PROJCS[\"WGS_1984_UTM_Zone_17N\",GEOGCS[\"GCS_WGS_1984\"],UNIT[\"Meter\",1.0]]",
+        );
+        assert_eq!(esri.epsg_code, 0);
+        assert_eq!(esri.name(), "WGS_1984_UTM_Zone_17N");
+        assert_eq!(
+            esri.matches(&CoordinateReferenceSystem::from_epsg(32617)),
+            Some(true)
+        );
+        assert_eq!(
+            esri.matches(&CoordinateReferenceSystem::from_epsg(32618)),
+            Some(false)
+        );
+        assert_eq!(esri.matches(&CoordinateReferenceSystem::default()), None);
+        assert_eq!(
+            crs.matches(&CoordinateReferenceSystem::new(32617, "", "")),
+            Some(false)
+        );
+
+        let utm = CoordinateReferenceSystem::new(32617, "", "");
+        assert_eq!(utm.units, CrsUnits::Metres);
+        let wkt2 = utm.wkt2().unwrap();
+        assert!(wkt2.starts_with("PROJCRS[\"WGS 84 / UTM zone 17N\""));
+        assert!(wkt2.contains("PARAMETER[\"Longitude of natural origin\",-81,"));
+        let from_wkt2 = CoordinateReferenceSystem::from_wkt(&wkt2);
+        assert!(from_wkt2.is_wkt2());
+        assert_eq!(from_wkt2.epsg_code, 32617);
+        assert_eq!(from_wkt2.units, CrsUnits::Metres);
+        assert_eq!(
+            CoordinateReferenceSystem::new(4326, "", "").units,
+            CrsUnits::Degrees
+        );
+        assert_eq!(
+            CoordinateReferenceSystem::new(0, "", "Foot_US").units,
+            CrsUnits::UsSurveyFeet
+        );
+        assert!(CoordinateReferenceSystem::new(2000, "", "")
+            .wkt2()
+            .is_none());
+    }
+}
@@ -1,3 +1,7 @@
+mod coordinate_reference_system;
 mod epsg_to_wkt;
+mod projection;
 
+pub use self::coordinate_reference_system::{CoordinateReferenceSystem, CrsUnits};
 pub use self::epsg_to_wkt::esri_wkt_from_epsg;
+pub use self::projection::{Crs, Transformation, EARTH_RADIUS};
@@ -0,0 +1,447 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: This module transforms coordinates between the coordinate reference systems that
+WhiteboxTools can reproject, i.e. geographic coordinates, Web Mercator, and the UTM zones.
+A transformation is a pipeline in the sense of PROJ's `+proj=pipeline` operations, i.e. the
+inverse of the source projection followed by the target projection, and can be described by
+the equivalent PROJ pipeline string. The operations are implemented here rather than by
+linking to the PROJ library, and so only the projections above are supported. Coordinate
+reference systems may be given by EPSG code, WKT, or PROJ string. There is no EPSG database
+either: the only EPSG codes that are recognized are those of the supported systems, i.e.
+4326, 4269, 4258, and 4617 (geographic), 3857 and 3785 (Web Mercator), 32601-32660 and
+32701-32760 (WGS84 UTM), 26901-26923 (NAD83 UTM), and 25828-25838 (ETRS89 UTM).
+
+The WGS84, NAD83, and ETRS89 datums are treated as one, since they differ by no more than
+about a metre or two and no datum shift grids are available; other datums (e.g. NAD27, ED50,
+or OSGB36) are not supported. The datum of a WKT definition is found from the name of its
+DATUM, or failing that its GEOGCS or PROJCS, and not from its TOWGS84 parameters.
+*/
+
+use super::esri_wkt_from_epsg;
+use std::io::Error;
+use crate::errors::ErrorCause;
+
+/// The semi-major axis of the WGS84 ellipsoid, which is also the radius of the sphere of the
+/// Web Mercator projection.
+pub const EARTH_RADIUS: f64 = 6378137.0;
+
+// The flattening of the WGS84 ellipsoid (the GRS80 ellipsoid of NAD83 differs by less than a
+// millimetre) and the UTM scale factor.
+const F: f64 = 1.0 / 298.257223563;
+const K0: f64 = 0.9996;
+
+/// A coordinate reference system that coordinates can be transformed to and from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Crs {
+    /// Longitude and latitude, in degrees.
+    Geographic,
+    /// The spherical Mercator projection of web maps (EPSG:3857).
+    WebMercator,
+    /// A zone of the Universal Transverse Mercator projection.
+    Utm { zone: i32, south: bool },
+}
+
+impl Crs {
+    /// Identifies a coordinate reference system from an EPSG code, which must be one of those
+    /// listed in the notes of this module; other codes, even of geographic or UTM systems,
+    /// return None.
+    pub fn from_epsg(epsg: u16) -> Option<Crs> {
+        let epsg = epsg as i32;
+        match epsg {
+            // WGS84, NAD83, ETRS89, and NAD83(CSRS)
+            4326 | 4269 | 4258 | 4617 => Some(Crs::Geographic),
+            3857 | 3785 => Some(Crs::WebMercator),
+            // WGS84, NAD83, and ETRS89 UTM zones
+            32601..=32660 => Some(Crs::Utm { zone: epsg - 32600, south: false }),
+            32701..=32760 => Some(Crs::Utm { zone: epsg - 32700, south: true }),
+            26901..=26923 => Some(Crs::Utm { zone: epsg - 26900, south: false }),
+            25828..=25838 => Some(Crs::Utm { zone: epsg - 25800, south: false }),
+            _ => None,
+        }
+    }
+
+    /// Identifies a coordinate reference system from an EPSG code and a WKT definition, either
+    /// of which may be missing (0 or empty). `geographic` indicates that the data are otherwise
+    /// known to be in geographic coordinates. Returns None if the system is not supported,
+    /// including when its datum is not WGS84, NAD83, or ETRS89.
+    pub fn from_definition(epsg: u16, wkt: &str, geographic: bool) -> Option<Crs> {
+        if let Some(crs) = Crs::from_epsg(epsg) {
+            if crs != Crs::Geographic || geographic {
+                return Some(crs);
+            }
+        }
+        let datum = wkt_datum_name(wkt);
+        match &datum {
+            Some(name) if !is_supported_datum(name) => return None,
+            // without a named datum, an unsupported EPSG code (other than the user-defined code,
+            // 32767) is all that is known of the system
+            None if epsg != 0 && epsg != 32767 && Crs::from_epsg(epsg).is_none() => return None,
+            _ => {}
+        }
+        let wkt = wkt.to_lowercase();
+        if wkt.contains("pseudo-mercator") || wkt.contains("pseudo_mercator") {
+            return Some(Crs::WebMercator);
+        }
+        if datum.is_some() && (wkt.contains("utm zone ") || wkt.contains("utm_zone_")) {
+            let i = wkt.find("utm zone ").or(wkt.find("utm_zone_")).unwrap() + 9;
+            let zone: String = wkt[i..].chars().take_while(|c| c.is_ascii_digit()).collect();
+            let hemisphere = wkt[i + zone.len()..].chars().next();
+            if let Ok(zone) = zone.parse::<i32>() {
+                if (1..=60).contains(&zone) {
+                    return Some(Crs::Utm { zone, south: hemisphere == Some('s') });
+                }
+            }
+        }
+        if geographic {
+            return Some(Crs::Geographic);
+        }
+        None
+    }
+
+    /// Parses a coordinate reference system given as an EPSG code (e.g. 'EPSG:32617' or
+    /// '32617'), a PROJ string (e.g. '+proj=utm +zone=17 +datum=WGS84'), or WKT.
+    pub fn parse(definition: &str) -> Result<Crs, Error> {
+        let definition = definition.trim();
+        let unsupported = || {
+            ErrorCause::UnsupportedFormat.error(
+                format!(
+                    "The coordinate reference system '{}' is not supported; it must be geographic, Web Mercator, or a UTM zone, on the WGS84, NAD83, or ETRS89 datum. EPSG codes other than 4326, 4269, 4258, 4617, 3857, 3785, 32601-32660, 32701-32760, 26901-26923, and 25828-25838 are not recognized.",
+                    definition
+                ),
+            )
+        };
+        let lower = definition.to_lowercase();
+        let code = lower
+            .strip_prefix("epsg:")
+            .or_else(|| lower.strip_prefix("+init=epsg:"))
+            .unwrap_or(&lower);
+        if let Ok(epsg) = code.trim().parse::<u16>() {
+            return Crs::from_epsg(epsg).ok_or_else(unsupported);
+        }
+        if lower.starts_with('+') || lower.starts_with("proj=") {
+            return Crs::from_proj_string(&lower).ok_or_else(unsupported);
+        }
+        if lower.contains('[') {
+            let geographic = lower.starts_with("geogcs[") || lower.starts_with("geogcrs[");
+            return Crs::from_definition(0, definition, geographic).ok_or_else(unsupported);
+        }
+        Err(unsupported())
+    }
+
+    fn from_proj_string(s: &str) -> Option<Crs> {
+        let mut proj = "";
+        let mut zone = None;
+        let mut south = false;
+        let mut spherical = false;
+        for token in s.split_whitespace() {
+            let token = token.trim_start_matches('+');
+            let (key, value) = token.split_once('=').unwrap_or((token, ""));
+            match key {
+                "proj" => proj = value,
+                "zone" => zone = value.parse::<i32>().ok(),
+                "south" => south = true,
+                "a" | "b" | "r" => spherical = value.parse::<f64>().ok() == Some(EARTH_RADIUS),
+                "init" => return Crs::from_epsg(value.trim_start_matches("epsg:").parse().ok()?),
+                _ => {}
+            }
+        }
+        match proj {
+            "longlat" | "latlong" | "lonlat" | "latlon" => Some(Crs::Geographic),
+            "webmerc" => Some(Crs::WebMercator),
+            "merc" if spherical => Some(Crs::WebMercator),
+            "utm" => match zone {
+                Some(zone) if (1..=60).contains(&zone) => Some(Crs::Utm { zone, south }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the EPSG code of the coordinate reference system, with WGS84 as the datum.
+    pub fn epsg_code(&self) -> u16 {
+        match *self {
+            Crs::Geographic => 4326,
+            Crs::WebMercator => 3857,
+            Crs::Utm { zone, south: false } => (32600 + zone) as u16,
+            Crs::Utm { zone, south: true } => (32700 + zone) as u16,
+        }
+    }
+
+    /// Returns the (Esri) WKT definition of the coordinate reference system.
+    pub fn wkt(&self) -> String {
+        esri_wkt_from_epsg(self.epsg_code())
+    }
+
+    /// Returns the PROJ string of the coordinate reference system.
+    pub fn proj_string(&self) -> String {
+        match *self {
+            Crs::Geographic => "+proj=longlat +datum=WGS84".to_string(),
+            Crs::WebMercator => "+proj=webmerc +datum=WGS84".to_string(),
+            Crs::Utm { zone, south } => format!(
+                "+proj=utm +zone={}{} +datum=WGS84",
+                zone,
+                if south { " +south" } else { "" }
+            ),
+        }
+    }
+
+    pub fn is_geographic(&self) -> bool {
+        *self == Crs::Geographic
+    }
+
+    /// Transforms a coordinate to longitude and latitude, in degrees.
+    pub fn to_lon_lat(&self, x: f64, y: f64) -> (f64, f64) {
+        match *self {
+            Crs::Geographic => (x, y),
+            Crs::WebMercator => (
+                (x / EARTH_RADIUS).to_degrees(),
+                (y / EARTH_RADIUS).sinh().atan().to_degrees(),
+            ),
+            Crs::Utm { zone, south } => utm_to_lon_lat(zone, south, x, y),
+        }
+    }
+
+    /// Transforms a longitude and latitude, in degrees, to a coordinate.
+    pub fn from_lon_lat(&self, lon: f64, lat: f64) -> (f64, f64) {
+        match *self {
+            Crs::Geographic => (lon, lat),
+            Crs::WebMercator => {
+                let lat = lat.to_radians();
+                (
+                    EARTH_RADIUS * lon.to_radians(),
+                    EARTH_RADIUS * (lat.tan() + 1f64 / lat.cos()).ln(),
+                )
+            }
+            Crs::Utm { zone, south } => lon_lat_to_utm(zone, south, lon, lat),
+        }
+    }
+}
+
+/// A transformation of coordinates from one coordinate reference system to another.
+#[derive(Clone, Copy, Debug)]
+pub struct Transformation {
+    pub source: Crs,
+    pub target: Crs,
+}
+
+impl Transformation {
+    pub fn new(source: Crs, target: Crs) -> Transformation {
+        Transformation { source, target }
+    }
+
+    pub fn transform(&self, x: f64, y: f64) -> (f64, f64) {
+        if self.source == self.target {
+            return (x, y);
+        }
+        let (lon, lat) = self.source.to_lon_lat(x, y);
+        self.target.from_lon_lat(lon, lat)
+    }
+
+    /// Returns the inverse transformation.
+    pub fn inverse(&self) -> Transformation {
+        Transformation::new(self.target, self.source)
+    }
+
+    /// Returns the PROJ pipeline string equivalent to the transformation, e.g. '+proj=pipeline
+    /// +step +inv +proj=utm +zone=17 +ellps=WGS84 +step +proj=webmerc +ellps=WGS84'.
+    pub fn pipeline(&self) -> String {
+        if self.source == self.target {
+            return "+proj=noop".to_string();
+        }
+        let step = |crs: &Crs| match *crs {
+            Crs::Geographic => "+proj=unitconvert +xy_in=rad +xy_out=deg".to_string(),
+            _ => crs.proj_string().replace("+datum=WGS84", "+ellps=WGS84"),
+        };
+        let inverse = match self.source {
+            // the inverse of a unit conversion from radians to degrees
+            Crs::Geographic => "+proj=unitconvert +xy_in=deg +xy_out=rad".to_string(),
+            _ => format!("+inv {}", step(&self.source)),
+        };
+        format!("+proj=pipeline +step {} +step {}", inverse, step(&self.target))
+    }
+}
+
+/// Projects a geographic coordinate to a UTM zone, using the series of Snyder (1987), Map
+/// Projections: A Working Manual, pp. 61.
+fn lon_lat_to_utm(zone: i32, south: bool, lon: f64, lat: f64) -> (f64, f64) {
+    let a = EARTH_RADIUS;
+    let e2 = F * (2.0 - F);
+    let ep2 = e2 / (1.0 - e2);
+    let lon0 = (zone as f64 * 6.0 - 183.0).to_radians();
+    let phi = lat.to_radians();
+    let n = a / (1.0 - e2 * phi.sin().powi(2)).sqrt();
+    let t = phi.tan().powi(2);
+    let c = ep2 * phi.cos().powi(2);
+    let aa = (lon.to_radians() - lon0) * phi.cos();
+    let m = a
+        * ((1.0 - e2 / 4.0 - 3.0 * e2 * e2 / 64.0 - 5.0 * e2.powi(3) / 256.0) * phi
+            - (3.0 * e2 / 8.0 + 3.0 * e2 * e2 / 32.0 + 45.0 * e2.powi(3) / 1024.0) * (2.0 * phi).sin()
+            + (15.0 * e2 * e2 / 256.0 + 45.0 * e2.powi(3) / 1024.0) * (4.0 * phi).sin()
+            - (35.0 * e2.powi(3) / 3072.0) * (6.0 * phi).sin());
+    let x = K0
+        * n
+        * (aa + (1.0 - t + c) * aa.powi(3) / 6.0
+            + (5.0 - 18.0 * t + t * t + 72.0 * c - 58.0 * ep2) * aa.powi(5) / 120.0)
+        + 500000.0;
+    let mut y = K0
+        * (m + n
+            * phi.tan()
+            * (aa * aa / 2.0
+                + (5.0 - t + 9.0 * c + 4.0 * c * c) * aa.powi(4) / 24.0
+                + (61.0 - 58.0 * t + t * t + 600.0 * c - 330.0 * ep2) * aa.powi(6) / 720.0));
+    if south {
+        y += 10000000.0;
+    }
+    (x, y)
+}
+
+/// The inverse of `lon_lat_to_utm`, after Snyder (1987), pp. 63-64.
+fn utm_to_lon_lat(zone: i32, south: bool, x: f64, y: f64) -> (f64, f64) {
+    let a = EARTH_RADIUS;
+    let e2 = F * (2.0 - F);
+    let ep2 = e2 / (1.0 - e2);
+    let lon0 = (zone as f64 * 6.0 - 183.0).to_radians();
+    let m = (if south { y - 10000000.0 } else { y }) / K0;
+    let mu = m / (a * (1.0 - e2 / 4.0 - 3.0 * e2 * e2 / 64.0 - 5.0 * e2.powi(3) / 256.0));
+    let e1 = (1.0 - (1.0 - e2).sqrt()) / (1.0 + (1.0 - e2).sqrt());
+    let phi1 = mu
+        + (3.0 * e1 / 2.0 - 27.0 * e1.powi(3) / 32.0) * (2.0 * mu).sin()
+        + (21.0 * e1 * e1 / 16.0 - 55.0 * e1.powi(4) / 32.0) * (4.0 * mu).sin()
+        + (151.0 * e1.powi(3) / 96.0) * (6.0 * mu).sin()
+        + (1097.0 * e1.powi(4) / 512.0) * (8.0 * mu).sin();
+    let c1 = ep2 * phi1.cos().powi(2);
+    let t1 = phi1.tan().powi(2);
+    let n1 = a / (1.0 - e2 * phi1.sin().powi(2)).sqrt();
+    let r1 = a * (1.0 - e2) / (1.0 - e2 * phi1.sin().powi(2)).powf(1.5);
+    let d = (x - 500000.0) / (n1 * K0);
+    let phi = phi1
+        - (n1 * phi1.tan() / r1)
+            * (d * d / 2.0
+                - (5.0 + 3.0 * t1 + 10.0 * c1 - 4.0 * c1 * c1 - 9.0 * ep2) * d.powi(4) / 24.0
+                + (61.0 + 90.0 * t1 + 298.0 * c1 + 45.0 * t1 * t1 - 252.0 * ep2 - 3.0 * c1 * c1)
+                    * d.powi(6)
+                    / 720.0);
+    let lon = lon0
+        + (d - (1.0 + 2.0 * t1 + c1) * d.powi(3) / 6.0
+            + (5.0 - 2.0 * c1 + 28.0 * t1 - 3.0 * c1 * c1 + 8.0 * ep2 + 24.0 * t1 * t1) * d.powi(5)
+                / 120.0)
+            / phi1.cos();
+    (lon.to_degrees(), phi.to_degrees())
+}
+
+/// Returns the name of the datum of a WKT definition, i.e. that of its DATUM node, or failing
+/// that of its geographic or projected coordinate reference system, which usually names the
+/// datum (e.g. 'GCS_WGS_1984' or 'NAD83 / UTM zone 17N'). Returns None if the definition has
+/// none of these nodes. The TOWGS84 parameters of a datum are ignored.
+fn wkt_datum_name(wkt: &str) -> Option<String> {
+    let lower = wkt.to_lowercase();
+    for keyword in ["datum[", "geogcs[", "geogcrs[", "basegeogcrs[", "projcs[", "projcrs["] {
+        let mut from = 0;
+        while let Some(i) = lower[from..].find(keyword).map(|i| i + from) {
+            from = i + keyword.len();
+            // a whole keyword, i.e. not VERT_DATUM or VDATUM
+            if lower[..i].ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_') {
+                continue;
+            }
+            let rest = lower[from..].trim_start();
+            if let Some(rest) = rest.strip_prefix('"') {
+                if let Some(end) = rest.find('"') {
+                    return Some(rest[..end].to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Returns true if a datum name, e.g. 'D_WGS_1984', 'WGS 84', 'North_American_Datum_1983', or
+/// 'ETRS89', is one of the WGS84, NAD83, or ETRS89 datums.
+fn is_supported_datum(name: &str) -> bool {
+    let name = name.trim().to_lowercase();
+    let name = name
+        .strip_prefix("d_")
+        .or_else(|| name.strip_prefix("gcs_"))
+        .unwrap_or(&name);
+    let name: String = name.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+    [
+        "wgs1984",
+        "wgs84",
+        "worldgeodeticsystem1984",
+        "nad83",
+        "nad1983",
+        "northamerican1983",
+        "northamericandatum1983",
+        "etrs89",
+        "etrs1989",
+        "europeanterrestrialreferencesystem1989",
+    ]
+    .iter()
+    .any(|prefix| name.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Crs, Transformation};
+
+    #[test]
+    fn test_parse_crs() {
+        assert_eq!(Crs::parse("EPSG:32617").unwrap(), Crs::Utm { zone: 17, south: false });
+        assert_eq!(Crs::parse("32717").unwrap(), Crs::Utm { zone: 17, south: true });
+        assert_eq!(Crs::parse("+proj=utm +zone=33 +south +datum=WGS84").unwrap(), Crs::Utm { zone: 33, south: true });
+        assert_eq!(Crs::parse("+proj=longlat +datum=WGS84").unwrap(), Crs::Geographic);
+        assert_eq!(Crs::parse("+proj=merc +a=6378137 +b=6378137").unwrap(), Crs::WebMercator);
+        assert_eq!(Crs::parse("GEOGCS[\"GCS_WGS_1984\"]").unwrap(), Crs::Geographic);
+        assert_eq!(
+            Crs::parse("PROJCS[\"WGS_1984_UTM_Zone_10N\"]").unwrap(),
+            Crs::Utm { zone: 10, south: false }
+        );
+        assert!(Crs::parse("EPSG:27700").is_err());
+        assert!(Crs::parse("+proj=lcc").is_err());
+    }
+
+    #[test]
+    fn test_crs_datums() {
+        let utm = Crs::Utm { zone: 17, south: false };
+        let wgs84 = "PROJCS[\"WGS 84 / UTM zone 17N\",GEOGCS[\"WGS 84\",DATUM[\"WGS_1984\",SPHEROID[\"WGS 84\",6378137,298.257223563]]],PROJECTION[\"Transverse_Mercator\"]]";
+        assert_eq!(Crs::from_definition(0, wgs84, false), Some(utm));
+        let nad83 = "PROJCS[\"NAD_1983_UTM_Zone_17N\",GEOGCS[\"GCS_North_American_1983\",DATUM[\"D_North_American_1983\",SPHEROID[\"GRS_1980\",6378137.0,298.257222101]]]]";
+        assert_eq!(Crs::from_definition(0, nad83, false), Some(utm));
+        // the TOWGS84 parameters of other datums do not make them WGS84
+        let ed50 = "PROJCS[\"ED50 / UTM zone 31N\",GEOGCS[\"ED50\",DATUM[\"European_Datum_1950\",SPHEROID[\"International 1924\",6378388,297],TOWGS84[-87,-98,-121,0,0,0,0]]]]";
+        assert_eq!(Crs::from_definition(0, ed50, false), None);
+        assert!(Crs::parse(ed50).is_err());
+        let nad27 = "GEOGCS[\"NAD27\",DATUM[\"North_American_Datum_1927\",SPHEROID[\"Clarke 1866\",6378206.4,294.9786982138982],TOWGS84[-8,160,176,0,0,0,0]]]";
+        assert_eq!(Crs::from_definition(0, nad27, true), None);
+        assert!(Crs::parse(nad27).is_err());
+        // an unrecognized EPSG code is only geographic when nothing else is known
+        assert_eq!(Crs::from_definition(4277, "", true), None);
+        assert_eq!(Crs::from_definition(0, "", true), Some(Crs::Geographic));
+    }
+
+    #[test]
+    fn test_transformation() {
+        // the origin of UTM zone 17 is on the equator at 81 degrees west
+        let to_utm = Transformation::new(Crs::Geographic, Crs::Utm { zone: 17, south: false });
+        let (x, y) = to_utm.transform(-81.0, 0.0);
+        assert!((x - 500000.0).abs() < 1e-6 && y.abs() < 1e-6);
+        // a point near Guelph, Ontario, checked against the series of Karney (2011)
+        let (x, y) = to_utm.transform(-80.25, 43.55);
+        assert!((x - 560_583.279).abs() < 0.01 && (y - 4_822_167.511).abs() < 0.01);
+        let (lon, lat) = to_utm.inverse().transform(x, y);
+        assert!((lon + 80.25).abs() < 1e-8 && (lat - 43.55).abs() < 1e-8);
+
+        let to_web = Transformation::new(Crs::Utm { zone: 17, south: false }, Crs::WebMercator);
+        let (x2, y2) = to_web.transform(x, y);
+        let (lon, lat) = Crs::WebMercator.to_lon_lat(x2, y2);
+        assert!((lon + 80.25).abs() < 1e-8 && (lat - 43.55).abs() < 1e-8);
+        assert_eq!(
+            to_web.pipeline(),
+            "+proj=pipeline +step +inv +proj=utm +zone=17 +ellps=WGS84 +step +proj=webmerc +ellps=WGS84"
+        );
+    }
+}
@@ -4,6 +4,7 @@
 use std::io::Error;
 use std::io::ErrorKind;
 use std::ops::{AddAssign, Index, IndexMut, SubAssign};
+use crate::errors::ErrorCause;
 
 /// A simple in-memory 2-D raster data structure that is not connected to a file.
 /// Pixel values can contain any data type or structure that implements the Copy,
@@ -147,8 +148,7 @@ where
 
     pub fn set_data_from_other(&mut self, other: &Array2D<T>) -> Result<(), Error> {
         if self.rows != other.rows || self.columns != other.columns {
-            return Err(Error::new(
-                ErrorKind::Other,
+            return Err(ErrorCause::GridMismatch.error(
                 "Rasters must have the same dimensions and extent.",
             ));
         }
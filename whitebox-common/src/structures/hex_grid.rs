@@ -0,0 +1,173 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+/// The geometry of a grid of pointy-topped hexagons stored as an 'odd-r' offset array, i.e. a
+/// rectangular array of rows in which every odd row is shifted east by half a cell. Every cell
+/// has six neighbours at the same distance (the cell `width`), which removes the directional
+/// bias of the square grid's cardinal and diagonal neighbours.
+///
+/// A hex grid is stored in an ordinary raster whose column spacing (`resolution_x`) is the hex
+/// width and whose row spacing (`resolution_y`) is `width * sqrt(3) / 2`. The centres of the
+/// cells in even rows therefore coincide with the centres of the raster cells, while those in
+/// odd rows lie half a cell further east.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HexGrid {
+    pub rows: isize,
+    pub columns: isize,
+    /// The western edge of the even rows.
+    pub west: f64,
+    pub north: f64,
+    /// The distance between the centres of neighbouring cells.
+    pub width: f64,
+}
+
+// neighbour offsets (row, column) of cells in even and odd rows, clockwise from the east
+const EVEN_ROW_OFFSETS: [(isize, isize); 6] = [(0, 1), (1, 0), (1, -1), (0, -1), (-1, -1), (-1, 0)];
+const ODD_ROW_OFFSETS: [(isize, isize); 6] = [(0, 1), (1, 1), (1, 0), (0, -1), (-1, 0), (-1, 1)];
+
+impl HexGrid {
+    /// Creates a new HexGrid.
+    pub fn new(rows: isize, columns: isize, west: f64, north: f64, width: f64) -> HexGrid {
+        HexGrid {
+            rows,
+            columns,
+            west,
+            north,
+            width,
+        }
+    }
+
+    /// Returns the hex grid stored in a raster with the given dimensions, position, and cell
+    /// spacing, or None if the row spacing is not that of a hex grid with the column spacing.
+    pub fn from_raster_grid(
+        rows: usize,
+        columns: usize,
+        west: f64,
+        north: f64,
+        resolution_x: f64,
+        resolution_y: f64,
+    ) -> Option<HexGrid> {
+        let grid = HexGrid::new(rows as isize, columns as isize, west, north, resolution_x);
+        if (resolution_y - grid.row_spacing()).abs() > 1e-3 * grid.row_spacing() {
+            return None;
+        }
+        Some(grid)
+    }
+
+    /// Creates the smallest hex grid with cells of a given width that covers an extent.
+    pub fn covering(west: f64, north: f64, east: f64, south: f64, width: f64) -> HexGrid {
+        let row_spacing = width * 3f64.sqrt() / 2f64;
+        let rows = ((north - south) / row_spacing).ceil().max(1f64) as isize;
+        let columns = ((east - west) / width).ceil().max(1f64) as isize;
+        HexGrid::new(rows, columns, west, north, width)
+    }
+
+    /// The distance between the centres of neighbouring rows.
+    pub fn row_spacing(&self) -> f64 {
+        self.width * 3f64.sqrt() / 2f64
+    }
+
+    /// The area of a cell.
+    pub fn cell_area(&self) -> f64 {
+        self.width * self.row_spacing()
+    }
+
+    /// The eastern edge of the storage raster.
+    pub fn east(&self) -> f64 {
+        self.west + self.columns as f64 * self.width
+    }
+
+    /// The southern edge of the storage raster.
+    pub fn south(&self) -> f64 {
+        self.north - self.rows as f64 * self.row_spacing()
+    }
+
+    /// Returns the (x, y) coordinates of the centre of a cell.
+    pub fn centre(&self, row: isize, column: isize) -> (f64, f64) {
+        let shift = if row & 1 == 1 { 0.5 } else { 0.0 };
+        (
+            self.west + (column as f64 + 0.5 + shift) * self.width,
+            self.north - (row as f64 + 0.5) * self.row_spacing(),
+        )
+    }
+
+    /// Returns the (row, column) of the six neighbours of a cell, clockwise from the east.
+    /// Neighbours may lie outside of the grid.
+    pub fn neighbours(&self, row: isize, column: isize) -> [(isize, isize); 6] {
+        let offsets = if row & 1 == 1 {
+            &ODD_ROW_OFFSETS
+        } else {
+            &EVEN_ROW_OFFSETS
+        };
+        let mut ret = [(0isize, 0isize); 6];
+        for i in 0..6 {
+            ret[i] = (row + offsets[i].0, column + offsets[i].1);
+        }
+        ret
+    }
+
+    pub fn is_in_grid(&self, row: isize, column: isize) -> bool {
+        row >= 0 && row < self.rows && column >= 0 && column < self.columns
+    }
+
+    /// Returns the (row, column) of the cell that contains a point, i.e. the cell with the
+    /// nearest centre, or None if that cell lies outside of the grid.
+    pub fn cell_containing(&self, x: f64, y: f64) -> Option<(isize, isize)> {
+        let r = ((self.north - y) / self.row_spacing() - 0.5).floor() as isize;
+        let mut nearest: Option<(isize, isize)> = None;
+        let mut min_dist = f64::INFINITY;
+        // the nearest centre lies in one of the two rows that bracket the point
+        for row in r..=r + 1 {
+            let shift = if row & 1 == 1 { 0.5 } else { 0.0 };
+            let c = ((x - self.west) / self.width - 0.5 - shift).floor() as isize;
+            for column in c..=c + 1 {
+                let (cx, cy) = self.centre(row, column);
+                let dist = (cx - x) * (cx - x) + (cy - y) * (cy - y);
+                if dist < min_dist {
+                    min_dist = dist;
+                    nearest = Some((row, column));
+                }
+            }
+        }
+        nearest.filter(|&(row, column)| self.is_in_grid(row, column))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HexGrid;
+
+    #[test]
+    fn test_neighbours_are_equidistant_and_mutual() {
+        let grid = HexGrid::new(10, 10, 0.0, 100.0, 2.0);
+        for &(row, column) in &[(4isize, 4isize), (5, 5)] {
+            let (x, y) = grid.centre(row, column);
+            for &(r, c) in grid.neighbours(row, column).iter() {
+                let (nx, ny) = grid.centre(r, c);
+                assert!((((nx - x).powi(2) + (ny - y).powi(2)).sqrt() - 2.0).abs() < 1e-9);
+                assert!(grid.neighbours(r, c).contains(&(row, column)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cell_containing() {
+        let grid = HexGrid::covering(0.0, 100.0, 100.0, 0.0, 5.0);
+        assert_eq!(grid.rows, 24);
+        assert_eq!(grid.columns, 20);
+        for row in 0..grid.rows {
+            for column in 0..grid.columns - 1 {
+                let (x, y) = grid.centre(row, column);
+                assert_eq!(grid.cell_containing(x, y), Some((row, column)));
+                // a point just inside of the edge shared with the eastern neighbour
+                assert_eq!(grid.cell_containing(x + 2.4, y), Some((row, column)));
+            }
+        }
+        assert_eq!(grid.cell_containing(-10.0, 50.0), None);
+    }
+}
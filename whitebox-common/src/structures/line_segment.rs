@@ -194,6 +194,23 @@ impl LineSegment {
         self.dist_to_segment_squared(p).sqrt()
     }
 
+    /// Returns the point on the segment that is nearest to p, along with the
+    /// position of that point expressed as a fraction of the segment length
+    /// measured from p1.
+    pub fn nearest_point_on_segment(&self, p: Point2D) -> (Point2D, f64) {
+        let l2 = self.p1.distance_squared(&self.p2);
+        if l2 == 0.0 { return (self.p1, 0f64) };
+        let mut t = ((p.x - self.p1.x) * (self.p2.x - self.p1.x) + (p.y - self.p1.y) * (self.p2.y - self.p1.y)) / l2;
+        t = 0f64.max(1f64.min(t));
+        (
+            Point2D::new(
+                self.p1.x + t * (self.p2.x - self.p1.x),
+                self.p1.y + t * (self.p2.y - self.p1.y)
+            ),
+            t
+        )
+    }
+
     // fn is_point_right_of_line(&self, p: Point2D) -> bool {
     //     (self.p2 - self.p1).cross(p - self.p1) < 0f64
     // }
@@ -3,9 +3,11 @@ mod array2d;
 mod bounding_box;
 mod circle;
 mod fixed_radius_search;
+mod hex_grid;
 mod line_segment;
 mod n_maximizer;
 mod n_minimizer;
+mod neighbourhood;
 mod point2d;
 mod point3d;
 mod polyline;
@@ -18,9 +20,11 @@ pub use self::array2d::Array2D;
 pub use self::bounding_box::BoundingBox;
 pub use self::circle::Circle;
 pub use self::fixed_radius_search::{DistanceMetric, FixedRadiusSearch2D, FixedRadiusSearch3D};
+pub use self::hex_grid::HexGrid;
 pub use self::line_segment::LineSegment;
 pub use self::n_maximizer::NMaximizer;
 pub use self::n_minimizer::NMinimizer;
+pub use self::neighbourhood::{EdgePolicy, FocalIterator, Neighbourhood};
 pub use self::point2d::Direction;
 pub use self::point2d::Point2D;
 pub use self::point3d::Point3D;
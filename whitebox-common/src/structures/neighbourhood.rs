@@ -0,0 +1,350 @@
+/////////////////////////////////////////////////////////////
+// Cell neighbourhoods used by focal (moving window) tools //
+/////////////////////////////////////////////////////////////
+
+/// How the neighbours of a cell that lie beyond the edges of a grid are handled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EdgePolicy {
+    /// Neighbours beyond the grid are returned unchanged, such that grid accessors like
+    /// `Raster::get_value` report them as NoData. This is the traditional behaviour of most tools.
+    NoData,
+    /// Neighbours beyond the grid are skipped.
+    Skip,
+    /// Neighbours beyond the grid are replaced by the nearest cell on the grid edge.
+    Nearest,
+    /// Neighbours beyond the grid are reflected back into the grid about its edges.
+    Reflect,
+    /// Neighbours beyond the grid wrap around to the opposite edge of the grid.
+    Wrap,
+}
+
+impl EdgePolicy {
+    /// Parses an edge policy from its name ('nodata', 'skip', 'nearest', 'reflect', or 'wrap').
+    pub fn parse(s: &str) -> Option<EdgePolicy> {
+        match s.trim().to_lowercase().as_str() {
+            "nodata" => Some(EdgePolicy::NoData),
+            "skip" => Some(EdgePolicy::Skip),
+            "nearest" => Some(EdgePolicy::Nearest),
+            "reflect" => Some(EdgePolicy::Reflect),
+            "wrap" => Some(EdgePolicy::Wrap),
+            _ => None,
+        }
+    }
+
+    /// Maps `index` onto a grid dimension of length `n`, returning `None` if the position is to be
+    /// skipped.
+    fn resolve(&self, index: isize, n: isize) -> Option<isize> {
+        if index >= 0 && index < n {
+            return Some(index);
+        }
+        match self {
+            EdgePolicy::NoData => Some(index),
+            EdgePolicy::Skip => None,
+            EdgePolicy::Nearest => Some(index.max(0).min(n - 1)),
+            EdgePolicy::Reflect => {
+                // reflection includes the edge cell, e.g. -1 maps to 0, consistent with Raster
+                let period = 2 * n;
+                let mut i = index.rem_euclid(period);
+                if i >= n {
+                    i = period - i - 1;
+                }
+                Some(i)
+            }
+            EdgePolicy::Wrap => Some(index.rem_euclid(n)),
+        }
+    }
+}
+
+/// The set of cells, defined by their row and column offsets, that form the neighbourhood of a
+/// grid cell, with an optional weight for each neighbour.
+///
+/// Example:
+///
+/// ```
+/// # use whitebox_common::structures::{Array2D, EdgePolicy, Neighbourhood};
+/// # fn main() -> std::io::Result<()> {
+/// # let (rows, columns, row, col) = (10isize, 10isize, 0isize, 0isize);
+/// # let input: Array2D<f64> = Array2D::new(rows, columns, 1f64, -32768f64)?;
+/// let d8 = Neighbourhood::d8();
+/// for (n, r, c) in d8.iter_at(row, col, rows, columns, EdgePolicy::Skip) {
+///     let zn = input.get_value(r, c);
+///     // ...
+/// #   let _ = (n, zn);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Neighbourhood {
+    /// Column offsets of the neighbours.
+    pub dx: Vec<isize>,
+    /// Row offsets of the neighbours.
+    pub dy: Vec<isize>,
+    /// Neighbour weights; these are all one for unweighted neighbourhoods.
+    pub weights: Vec<f64>,
+}
+
+impl Neighbourhood {
+    /// Creates a neighbourhood from column (`dx`) and row (`dy`) offsets, with unit weights.
+    pub fn new(dx: Vec<isize>, dy: Vec<isize>) -> Neighbourhood {
+        assert_eq!(dx.len(), dy.len(), "The offset vectors must have equal length.");
+        let weights = vec![1f64; dx.len()];
+        Neighbourhood { dx, dy, weights }
+    }
+
+    /// The eight immediate neighbours, ordered clockwise from the northeast cell. The neighbour
+    /// index `i` corresponds to the D8 flow direction pointer value `2^i`.
+    pub fn d8() -> Neighbourhood {
+        Neighbourhood::new(
+            vec![1, 1, 1, 0, -1, -1, -1, 0],
+            vec![-1, 0, 1, 1, 1, 0, -1, -1],
+        )
+    }
+
+    /// The four neighbours sharing an edge with a cell, ordered clockwise from the east cell.
+    pub fn d4() -> Neighbourhood {
+        Neighbourhood::new(vec![1, 0, -1, 0], vec![0, 1, 0, -1])
+    }
+
+    /// The cells at a Chebyshev distance of exactly `radius` from a cell, i.e. the square ring of
+    /// `8 x radius` cells, ordered clockwise from the northeast corner. `ring(1)` is equivalent to
+    /// `d8()`.
+    pub fn ring(radius: usize) -> Neighbourhood {
+        let r = radius as isize;
+        let (mut dx, mut dy) = (vec![], vec![]);
+        if r == 0 {
+            return Neighbourhood::new(vec![0], vec![0]);
+        }
+        // east side, moving south
+        for y in -r..r {
+            dx.push(r);
+            dy.push(y);
+        }
+        // south side, moving west
+        for x in (-r + 1..=r).rev() {
+            dx.push(x);
+            dy.push(r);
+        }
+        // west side, moving north
+        for y in (-r + 1..=r).rev() {
+            dx.push(-r);
+            dy.push(y);
+        }
+        // north side, moving east
+        for x in -r..r {
+            dx.push(x);
+            dy.push(-r);
+        }
+        Neighbourhood::new(dx, dy)
+    }
+
+    /// A rectangular window of `rows` by `columns` cells, which should be odd numbers, centred on a
+    /// cell and ordered row by row. The centre cell is included if `include_centre` is true.
+    pub fn window(rows: usize, columns: usize, include_centre: bool) -> Neighbourhood {
+        let (half_rows, half_cols) = ((rows / 2) as isize, (columns / 2) as isize);
+        Neighbourhood::from_predicate(half_rows, half_cols, |x, y| {
+            include_centre || x != 0 || y != 0
+        })
+    }
+
+    /// The cells whose centres are within `radius` cells of a cell's centre, ordered row by row.
+    /// The centre cell is included if `include_centre` is true.
+    pub fn circle(radius: f64, include_centre: bool) -> Neighbourhood {
+        let half = radius.floor() as isize;
+        let r2 = radius * radius;
+        Neighbourhood::from_predicate(half, half, |x, y| {
+            (x * x + y * y) as f64 <= r2 && (include_centre || x != 0 || y != 0)
+        })
+    }
+
+    /// The cells whose centres are more than `inner_radius` cells and no more than `outer_radius`
+    /// cells from a cell's centre, ordered row by row.
+    pub fn annulus(inner_radius: f64, outer_radius: f64) -> Neighbourhood {
+        let half = outer_radius.floor() as isize;
+        let (inner2, outer2) = (inner_radius * inner_radius, outer_radius * outer_radius);
+        Neighbourhood::from_predicate(half, half, |x, y| {
+            let d2 = (x * x + y * y) as f64;
+            d2 > inner2 && d2 <= outer2
+        })
+    }
+
+    /// Creates a weighted neighbourhood from a kernel of `rows` by `columns` weights stored in
+    /// row-major order, with the kernel cell at (`centre_row`, `centre_col`) aligned with the
+    /// focal cell. Cells with zero weight are excluded.
+    pub fn from_kernel(
+        weights: &[f64],
+        rows: usize,
+        columns: usize,
+        centre_row: isize,
+        centre_col: isize,
+    ) -> Neighbourhood {
+        assert_eq!(weights.len(), rows * columns, "The kernel size does not match its weights.");
+        let (mut dx, mut dy, mut w) = (vec![], vec![], vec![]);
+        for r in 0..rows {
+            for c in 0..columns {
+                if weights[r * columns + c] != 0f64 {
+                    dx.push(c as isize - centre_col);
+                    dy.push(r as isize - centre_row);
+                    w.push(weights[r * columns + c]);
+                }
+            }
+        }
+        Neighbourhood { dx, dy, weights: w }
+    }
+
+    fn from_predicate<F: Fn(isize, isize) -> bool>(
+        half_rows: isize,
+        half_cols: isize,
+        include: F,
+    ) -> Neighbourhood {
+        let (mut dx, mut dy) = (vec![], vec![]);
+        for y in -half_rows..=half_rows {
+            for x in -half_cols..=half_cols {
+                if include(x, y) {
+                    dx.push(x);
+                    dy.push(y);
+                }
+            }
+        }
+        Neighbourhood::new(dx, dy)
+    }
+
+    /// Returns the number of cells in the neighbourhood.
+    pub fn len(&self) -> usize {
+        self.dx.len()
+    }
+
+    /// Returns true if the neighbourhood contains no cells.
+    pub fn is_empty(&self) -> bool {
+        self.dx.is_empty()
+    }
+
+    /// Returns the largest row and column offset of any neighbour.
+    pub fn radius(&self) -> isize {
+        self.dx
+            .iter()
+            .chain(self.dy.iter())
+            .map(|d| d.abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Iterates over the neighbours of the cell at (`row`, `col`) in a grid of `rows` by
+    /// `columns` cells, yielding the neighbour index and its row and column, with neighbours
+    /// beyond the grid handled according to `edges`.
+    pub fn iter_at(
+        &self,
+        row: isize,
+        col: isize,
+        rows: isize,
+        columns: isize,
+        edges: EdgePolicy,
+    ) -> FocalIterator<'_> {
+        FocalIterator {
+            neighbourhood: self,
+            row,
+            col,
+            rows,
+            columns,
+            edges,
+            index: 0,
+        }
+    }
+}
+
+/// Iterator over the neighbours of a cell, created by `Neighbourhood::iter_at`. Each item is a
+/// tuple of the neighbour index within the neighbourhood and the neighbour's row and column.
+pub struct FocalIterator<'a> {
+    neighbourhood: &'a Neighbourhood,
+    row: isize,
+    col: isize,
+    rows: isize,
+    columns: isize,
+    edges: EdgePolicy,
+    index: usize,
+}
+
+impl<'a> Iterator for FocalIterator<'a> {
+    type Item = (usize, isize, isize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.neighbourhood.len() {
+            let i = self.index;
+            self.index += 1;
+            let r = self.row + self.neighbourhood.dy[i];
+            let c = self.col + self.neighbourhood.dx[i];
+            if let (Some(r), Some(c)) = (
+                self.edges.resolve(r, self.rows),
+                self.edges.resolve(c, self.columns),
+            ) {
+                return Some((i, r, c));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EdgePolicy, Neighbourhood};
+
+    #[test]
+    fn test_ring_one_is_d8() {
+        let ring = Neighbourhood::ring(1);
+        let d8 = Neighbourhood::d8();
+        assert_eq!(ring.dx, d8.dx);
+        assert_eq!(ring.dy, d8.dy);
+    }
+
+    #[test]
+    fn test_ring_two() {
+        // the extended neighbourhood used when breaching single-cell pits
+        let ring = Neighbourhood::ring(2);
+        assert_eq!(ring.dx, vec![2, 2, 2, 2, 2, 1, 0, -1, -2, -2, -2, -2, -2, -1, 0, 1]);
+        assert_eq!(ring.dy, vec![-2, -1, 0, 1, 2, 2, 2, 2, 2, 1, 0, -1, -2, -2, -2, -2]);
+    }
+
+    #[test]
+    fn test_window_and_circle() {
+        assert_eq!(Neighbourhood::window(3, 5, true).len(), 15);
+        assert_eq!(Neighbourhood::window(3, 3, false).len(), 8);
+        assert_eq!(Neighbourhood::circle(1.0, true).len(), 5);
+        assert_eq!(Neighbourhood::circle(1.5, false).len(), 8);
+        assert_eq!(Neighbourhood::annulus(1.0, 2.0).len(), 8);
+        assert_eq!(Neighbourhood::circle(2.0, true).radius(), 2);
+    }
+
+    #[test]
+    fn test_from_kernel() {
+        let n = Neighbourhood::from_kernel(&[0.0, 1.0, 0.0, 2.0, 0.0, 3.0], 2, 3, 0, 1);
+        assert_eq!(n.dx, vec![0, -1, 1]);
+        assert_eq!(n.dy, vec![0, 1, 1]);
+        assert_eq!(n.weights, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_edge_policies() {
+        let d4 = Neighbourhood::d4();
+        // cell in the upper-left corner of a 3 x 3 grid
+        let cells = |edges| d4.iter_at(0, 0, 3, 3, edges).collect::<Vec<_>>();
+        assert_eq!(cells(EdgePolicy::Skip), vec![(0, 0, 1), (1, 1, 0)]);
+        assert_eq!(
+            cells(EdgePolicy::NoData),
+            vec![(0, 0, 1), (1, 1, 0), (2, 0, -1), (3, -1, 0)]
+        );
+        assert_eq!(
+            cells(EdgePolicy::Nearest),
+            vec![(0, 0, 1), (1, 1, 0), (2, 0, 0), (3, 0, 0)]
+        );
+        assert_eq!(
+            cells(EdgePolicy::Reflect),
+            vec![(0, 0, 1), (1, 1, 0), (2, 0, 0), (3, 0, 0)]
+        );
+        assert_eq!(
+            cells(EdgePolicy::Wrap),
+            vec![(0, 0, 1), (1, 1, 0), (2, 0, 2), (3, 2, 0)]
+        );
+        assert_eq!(EdgePolicy::parse("Reflect"), Some(EdgePolicy::Reflect));
+        assert_eq!(EdgePolicy::parse("mirror"), None);
+    }
+}
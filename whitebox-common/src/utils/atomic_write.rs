@@ -0,0 +1,181 @@
+/////////////////////////////////////////
+// Atomic writing of output data files //
+/////////////////////////////////////////
+
+use super::{is_url, object_exists, upload_object};
+use crate::configs::get_configs;
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static TEMP_DIR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes an output file such that it either appears complete or not at all.
+///
+/// The `write` function is given the name of a file in a temporary directory created next
+/// to `file_name`. When it succeeds, every file that it created there, including sidecar
+/// files such as `.hdr` or `.dbf` files, is renamed into place, with the main file renamed
+/// last. When it fails, the temporary files are removed and any existing output is left
+/// untouched. If the `no_overwrite` setting is enabled, an error is returned before any
+/// writing takes place when `file_name` already exists.
+///
+/// When `file_name` is a URL, e.g. an `s3://`, `gs://`, or `az://` URI, the temporary
+/// directory is created in the system temporary directory and the files are uploaded to the
+/// object store, alongside `file_name`, in the same order.
+///
+/// The output may also be a directory, e.g. a Zarr store. An existing directory of the same
+/// name is replaced as a whole, and when uploading, the files of its subdirectories are uploaded
+/// before those at its root, where its metadata are kept.
+pub fn write_atomically<F>(file_name: &str, write: F) -> Result<(), Error>
+where
+    F: FnOnce(&str) -> Result<(), Error>,
+{
+    let path = Path::new(file_name);
+    let to_object_store = is_url(file_name);
+    // objects are only checked when needed, since it takes a request to the object store
+    let exists = if to_object_store {
+        get_configs()?.no_overwrite && object_exists(file_name)?
+    } else {
+        path.exists()
+    };
+    if exists && get_configs()?.no_overwrite {
+        return Err(Error::new(
+            ErrorKind::AlreadyExists,
+            format!(
+                "The output file {} already exists and the no_overwrite option is set.",
+                file_name
+            ),
+        ));
+    }
+    let short_name = match path.file_name() {
+        Some(name) if !file_name.ends_with('/') => name.to_os_string(),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{} is not a valid output file name.", file_name),
+            ))
+        }
+    };
+    let dir = match path.parent() {
+        _ if to_object_store => env::temp_dir(),
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => Path::new(".").to_path_buf(),
+    };
+    let temp_dir = dir.join(format!(
+        ".wbtmp-{}-{}",
+        process::id(),
+        TEMP_DIR_COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    fs::create_dir(&temp_dir)?;
+    let temp_file = temp_dir.join(&short_name);
+
+    let result = write(&temp_file.to_string_lossy()).and_then(|_| {
+        let mut entries = vec![];
+        for entry in fs::read_dir(&temp_dir)? {
+            entries.push(entry?.file_name());
+        }
+        // sidecar files first, so that the main file never appears without them
+        entries.sort_by_key(|name| *name == short_name);
+        for name in entries {
+            let source = temp_dir.join(&name);
+            if to_object_store {
+                let prefix = &file_name[..file_name.rfind('/').unwrap_or(0)];
+                let url = format!("{}/{}", prefix, name.to_string_lossy());
+                upload_path(&source, &url)?;
+            } else if source.is_dir() && dir.join(&name).exists() {
+                // a directory can't be renamed onto an existing one, which is set aside in the
+                // temporary directory, to be removed along with it
+                let replaced = temp_dir.join(".replaced");
+                fs::rename(dir.join(&name), &replaced)?;
+                if let Err(e) = fs::rename(&source, dir.join(&name)) {
+                    let _ = fs::rename(&replaced, dir.join(&name));
+                    return Err(e);
+                }
+            } else {
+                fs::rename(&source, dir.join(&name))?;
+            }
+        }
+        Ok(())
+    });
+    let _ = fs::remove_dir_all(&temp_dir);
+    result
+}
+
+/// Uploads a file, or the files of a directory, to the object named by a URL, or to the objects
+/// under it. The files of subdirectories are uploaded first.
+fn upload_path(path: &Path, url: &str) -> Result<(), Error> {
+    if !path.is_dir() {
+        return upload_object(&path.to_string_lossy(), url);
+    }
+    let mut entries = vec![];
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        entries.push((!entry.file_type()?.is_dir(), entry.file_name()));
+    }
+    entries.sort();
+    for (_, name) in entries {
+        upload_path(
+            &path.join(&name),
+            &format!("{}/{}", url, name.to_string_lossy()),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::write_atomically;
+    use std::fs;
+    use std::io::{Error, ErrorKind};
+
+    #[test]
+    fn test_writes_main_and_sidecar_files() {
+        let dir = std::env::temp_dir().join(format!("wbt_atomic_write_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file_name = dir.join("out.flt").to_string_lossy().to_string();
+        write_atomically(&file_name, |tmp| {
+            fs::write(tmp, "data")?;
+            fs::write(std::path::Path::new(tmp).with_extension("hdr"), "header")
+        })
+        .unwrap();
+        assert_eq!(fs::read_to_string(&file_name).unwrap(), "data");
+        assert_eq!(fs::read_to_string(dir.join("out.hdr")).unwrap(), "header");
+
+        // a failed write leaves the existing output in place
+        let result = write_atomically(&file_name, |tmp| {
+            fs::write(tmp, "partial")?;
+            Err(Error::new(ErrorKind::Other, "interrupted"))
+        });
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&file_name).unwrap(), "data");
+        let leftovers = fs::read_dir(&dir).unwrap().count();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(leftovers, 2);
+    }
+
+    #[test]
+    fn test_replaces_directory_outputs() {
+        let dir = std::env::temp_dir().join(format!("wbt_atomic_write_dir_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file_name = dir.join("out.zarr").to_string_lossy().to_string();
+        for contents in ["first", "second"] {
+            write_atomically(&file_name, |tmp| {
+                fs::create_dir_all(std::path::Path::new(tmp).join("data"))?;
+                fs::write(std::path::Path::new(tmp).join(contents), contents)?;
+                fs::write(std::path::Path::new(tmp).join("data").join("0.0"), contents)
+            })
+            .unwrap();
+        }
+        let out = std::path::Path::new(&file_name);
+        let has_first = out.join("first").exists();
+        let data = fs::read_to_string(out.join("data").join("0.0")).unwrap();
+        let leftovers = fs::read_dir(&dir).unwrap().count();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(!has_first);
+        assert_eq!(data, "second");
+        assert_eq!(leftovers, 1);
+    }
+}
@@ -0,0 +1,189 @@
+////////////////////////////////////////////////////////
+// A disk cache of the blocks of remote files         //
+////////////////////////////////////////////////////////
+
+use std::env;
+use std::fs;
+use std::io::Error;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::SystemTime;
+
+/// The name of the directory, within the system's temporary directory, holding cached blocks.
+const CACHE_DIR_NAME: &str = "whitebox_tools_remote_cache";
+
+/// A cache, on disk, of the blocks of one remote object that have been fetched with range
+/// requests, such that tools run repeatedly on the same object (e.g. a cloud-hosted DEM) need
+/// not transfer it again. Each object has a directory of block files, named by a hash of its
+/// URL, size, and validator (its ETag or last-modified time), so a changed object is never
+/// read from a stale cache. The least recently used blocks of all objects are removed once
+/// the cache exceeds its size limit.
+pub(super) struct BlockCache {
+    root: PathBuf,
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl BlockCache {
+    /// Returns the cache of an object, or None if the `remote_cache_mb` setting disables the
+    /// cache or the object has no validator with which to detect changes.
+    pub(super) fn for_object(url: &str, size: u64, validator: Option<&str>) -> Option<BlockCache> {
+        let validator = validator?;
+        let cache_mb = crate::configs::get_configs().ok()?.remote_cache_mb;
+        if cache_mb == 0 {
+            return None;
+        }
+        let root = env::temp_dir().join(CACHE_DIR_NAME);
+        Some(BlockCache::new(
+            &root,
+            url,
+            size,
+            validator,
+            cache_mb as u64 * 1_048_576,
+        ))
+    }
+
+    fn new(root: &Path, url: &str, size: u64, validator: &str, max_bytes: u64) -> BlockCache {
+        // the query of a URL may hold a signature that differs from one request to the next
+        let base_url = url.split('?').next().unwrap_or(url);
+        let key = format!("{}\n{}\n{}", base_url, size, validator);
+        BlockCache {
+            root: root.to_path_buf(),
+            dir: root.join(format!("{:016x}", fnv1a(key.as_bytes()))),
+            max_bytes,
+        }
+    }
+
+    fn block_file(&self, index: u64) -> PathBuf {
+        self.dir.join(format!("{}.blk", index))
+    }
+
+    /// Returns a cached block, provided that it holds the expected number of bytes.
+    pub(super) fn get(&self, index: u64, len: usize) -> Option<Vec<u8>> {
+        let file_name = self.block_file(index);
+        let data = fs::read(&file_name).ok()?;
+        if data.len() != len {
+            let _ = fs::remove_file(&file_name);
+            return None;
+        }
+        // mark the block as recently used
+        if let Ok(f) = fs::File::options().write(true).open(&file_name) {
+            let _ = f.set_modified(SystemTime::now());
+        }
+        Some(data)
+    }
+
+    /// Adds a block to the cache, and then removes the least recently used blocks if the
+    /// cache exceeds its size limit. Failures are ignored, since the cache is only an
+    /// optimization.
+    pub(super) fn put(&self, index: u64, data: &[u8]) {
+        if data.len() as u64 > self.max_bytes || fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        // written under a temporary name, so that concurrent processes never read partial blocks
+        let file_name = self.block_file(index);
+        let temp_file = self.dir.join(format!("{}.{}.tmp", index, process::id()));
+        if fs::write(&temp_file, data).is_err() || fs::rename(&temp_file, &file_name).is_err() {
+            let _ = fs::remove_file(&temp_file);
+            return;
+        }
+        let _ = self.trim();
+    }
+
+    fn trim(&self) -> Result<(), Error> {
+        let mut blocks = vec![];
+        let mut total = 0u64;
+        for dir in fs::read_dir(&self.root)? {
+            let dir = dir?.path();
+            if !dir.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                if path.extension().is_some_and(|e| e == "blk") {
+                    let metadata = entry.metadata()?;
+                    total += metadata.len();
+                    blocks.push((metadata.modified()?, metadata.len(), path));
+                }
+            }
+        }
+        if total <= self.max_bytes {
+            return Ok(());
+        }
+        blocks.sort_by_key(|b| b.0);
+        for (_, len, path) in blocks {
+            if total <= self.max_bytes {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total -= len;
+                if let Some(dir) = path.parent() {
+                    // only succeeds once the directory is empty
+                    let _ = fs::remove_dir(dir);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The 64-bit FNV-1a hash, which, unlike the standard library's hasher, is stable across
+/// versions of Rust, as is needed to name cached blocks persistently.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fnv1a, BlockCache};
+    use std::env;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_caches_blocks() {
+        let root = env::temp_dir().join(format!("wbt_block_cache_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let url = "https://example.com/dem.tif";
+        let cache = BlockCache::new(&root, &format!("{}?sig=1", url), 100, "\"abc\"", 25);
+        assert!(cache.get(0, 10).is_none());
+        cache.put(0, &[1u8; 10]);
+        assert_eq!(cache.get(0, 10), Some(vec![1u8; 10]));
+        // a block of an unexpected length is discarded
+        assert!(cache.get(0, 11).is_none());
+        assert!(cache.get(0, 10).is_none());
+
+        // the signature of a URL doesn't change the key, but the validator does
+        cache.put(1, &[2u8; 10]);
+        let resigned = BlockCache::new(&root, &format!("{}?sig=2", url), 100, "\"abc\"", 25);
+        assert_eq!(resigned.get(1, 10), Some(vec![2u8; 10]));
+        let changed = BlockCache::new(&root, url, 100, "\"def\"", 25);
+        assert!(changed.get(1, 10).is_none());
+
+        // the least recently used blocks are removed once the cache exceeds its limit
+        let hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        let f = fs::File::options()
+            .write(true)
+            .open(cache.block_file(1))
+            .unwrap();
+        f.set_modified(hour_ago).unwrap();
+        changed.put(0, &[3u8; 10]);
+        changed.put(1, &[4u8; 10]);
+        assert!(cache.get(1, 10).is_none());
+        assert_eq!(changed.get(0, 10), Some(vec![3u8; 10]));
+        assert_eq!(changed.get(1, 10), Some(vec![4u8; 10]));
+        let _ = fs::remove_dir_all(&root);
+    }
+}
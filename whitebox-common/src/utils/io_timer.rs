@@ -0,0 +1,45 @@
+/////////////////////////////////////////////////////////
+// Accumulated time spent reading and writing datasets //
+/////////////////////////////////////////////////////////
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+static IO_NANOS: AtomicU64 = AtomicU64::new(0);
+
+/// Runs `f`, a dataset read or write, and adds the time that it takes to the process-wide
+/// I/O time. Reads and writes that run concurrently on several threads are each counted in
+/// full, so the total can exceed the wall-clock time of a tool run.
+pub fn time_io<T, F: FnOnce() -> T>(f: F) -> T {
+    let start = Instant::now();
+    let ret = f();
+    IO_NANOS.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+    ret
+}
+
+/// Returns the time recorded by `time_io` since the last call to `reset_io_time`.
+pub fn io_time() -> Duration {
+    Duration::from_nanos(IO_NANOS.load(Ordering::Relaxed))
+}
+
+pub fn reset_io_time() {
+    IO_NANOS.store(0, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{io_time, time_io};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn test_time_io_accumulates() {
+        let before = io_time();
+        let value = time_io(|| {
+            thread::sleep(Duration::from_millis(5));
+            42
+        });
+        assert_eq!(value, 42);
+        assert!(io_time() - before >= Duration::from_millis(5));
+    }
+}
@@ -0,0 +1,169 @@
+///////////////////////////////////
+// LZW compression, as used by TIFF //
+///////////////////////////////////
+
+use std::collections::HashMap;
+
+const CLEAR_CODE: u32 = 256;
+const EOI_CODE: u32 = 257;
+const FIRST_CODE: u32 = 258;
+const MIN_CODE_LEN: u32 = 9;
+/// The table is reset once it holds this many codes, leaving room within 12-bit codes.
+const MAX_CODE: u32 = 4094;
+
+/// Compresses `input` using the variant of LZW used by TIFF files (compression 5), i.e. with
+/// codes written most-significant bit first and the code length increased one code early,
+/// as is done by libtiff. The output begins with a Clear code and ends with an EndOfInformation
+/// code.
+pub fn lzw_compress(input: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter {
+        out: Vec::with_capacity(input.len() / 2 + 16),
+        acc: 0,
+        num_bits: 0,
+    };
+    let mut code_len = MIN_CODE_LEN;
+    writer.write(CLEAR_CODE, code_len);
+    if input.is_empty() {
+        writer.write(EOI_CODE, code_len);
+        return writer.finish();
+    }
+
+    // the codes of the known strings, keyed by the code of their prefix and their last byte
+    let mut table: HashMap<u32, u32> = HashMap::new();
+    let mut next_code = FIRST_CODE;
+    let mut prefix = input[0] as u32;
+    for &b in &input[1..] {
+        let key = (prefix << 8) | b as u32;
+        if let Some(&code) = table.get(&key) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix, code_len);
+        prefix = b as u32;
+        table.insert(key, next_code);
+        next_code += 1;
+        if next_code == MAX_CODE {
+            writer.write(CLEAR_CODE, code_len);
+            table.clear();
+            next_code = FIRST_CODE;
+            code_len = MIN_CODE_LEN;
+        } else if next_code > (1 << code_len) - 1 {
+            code_len += 1;
+        }
+    }
+    writer.write(prefix, code_len);
+    // the decoder adds an entry for the final code, which may lengthen the EOI code
+    next_code += 1;
+    if next_code == MAX_CODE {
+        writer.write(CLEAR_CODE, code_len);
+        code_len = MIN_CODE_LEN;
+    } else if next_code > (1 << code_len) - 1 {
+        code_len += 1;
+    }
+    writer.write(EOI_CODE, code_len);
+    writer.finish()
+}
+
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u32,
+    num_bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u32, code_len: u32) {
+        self.acc = (self.acc << code_len) | code;
+        self.num_bits += code_len;
+        while self.num_bits >= 8 {
+            self.num_bits -= 8;
+            self.out.push((self.acc >> self.num_bits) as u8);
+        }
+        self.acc &= (1 << self.num_bits) - 1;
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.num_bits > 0 {
+            self.out.push((self.acc << (8 - self.num_bits)) as u8);
+        }
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::lzw_compress;
+
+    /// A TIFF LZW decoder, following the description in the TIFF 6.0 specification.
+    fn decompress(input: &[u8]) -> Vec<u8> {
+        let mut out = vec![];
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut code_len = 9u32;
+        let (mut acc, mut num_bits, mut pos) = (0u64, 0u32, 0usize);
+        let mut old: Option<usize> = None;
+        loop {
+            while num_bits < code_len {
+                acc = (acc << 8) | input[pos] as u64;
+                pos += 1;
+                num_bits += 8;
+            }
+            num_bits -= code_len;
+            let code = ((acc >> num_bits) & ((1 << code_len) - 1)) as usize;
+            if code == 257 {
+                return out;
+            }
+            if code == 256 {
+                table = (0..256).map(|b| vec![b as u8]).collect();
+                table.push(vec![]);
+                table.push(vec![]);
+                code_len = 9;
+                old = None;
+                continue;
+            }
+            let entry = if code < table.len() {
+                table[code].clone()
+            } else {
+                // the code is the one about to be added to the table
+                let mut e = table[old.unwrap()].clone();
+                e.push(e[0]);
+                e
+            };
+            out.extend_from_slice(&entry);
+            if let Some(o) = old {
+                let mut e = table[o].clone();
+                e.push(entry[0]);
+                table.push(e);
+            }
+            old = Some(code);
+            if table.len() + 1 >= (1 << code_len) && code_len < 12 {
+                code_len += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut inputs: Vec<Vec<u8>> = vec![vec![], b"a".to_vec(), b"TOBEORNOTTOBEORTOBEORNOT".to_vec(), vec![7u8; 100_000]];
+        // pseudo-random bytes, which fill the table repeatedly, and a repetitive pattern
+        let mut x = 12345u32;
+        inputs.push(
+            (0..50_000)
+                .map(|_| {
+                    x = x.wrapping_mul(1103515245).wrapping_add(12345);
+                    (x >> 16) as u8
+                })
+                .collect(),
+        );
+        inputs.push((0..70_000u32).map(|i| (i % 251 + i / 1000) as u8).collect());
+        for input in inputs {
+            let compressed = lzw_compress(&input);
+            assert_eq!(decompress(&compressed), input);
+        }
+        assert!(lzw_compress(&vec![7u8; 100_000]).len() < 1_000);
+    }
+
+    #[test]
+    fn test_reference_codes() {
+        // Clear, 'a', 'b', the new code for 'ab', then EndOfInformation, as 9-bit codes
+        assert_eq!(lzw_compress(b"abab"), vec![0x80, 0x18, 0x4c, 0x50, 0x28, 0x08]);
+    }
+}
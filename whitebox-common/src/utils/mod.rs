@@ -1,11 +1,32 @@
 // private sub-module defined in other files
+mod atomic_write;
+mod block_cache;
 mod byte_order_reader;
 mod byte_order_writer;
+mod io_timer;
+mod lzw;
+mod object_store;
+mod paths;
+mod sqlite;
+mod threads;
 
 // exports identifiers from private sub-modules in the current module namespace
+pub use self::atomic_write::write_atomically;
 pub use self::byte_order_reader::ByteOrderReader;
 pub use self::byte_order_reader::Endianness;
 pub use self::byte_order_writer::ByteOrderWriter;
+pub use self::io_timer::{io_time, reset_io_time, time_io};
+pub use self::lzw::lzw_compress;
+pub use self::object_store::{
+    download_object, object_exists, open_input, post_json, read_input, upload_object, ObjectReader,
+    ReadSeek,
+};
+pub use self::paths::{
+    is_memory_dataset, is_postgis_dataset, is_sqlite_dataset, is_url, resolve_input_path,
+    resolve_path,
+};
+pub use self::sqlite::{SqlValue, SqliteReader, SqliteWriter};
+pub use self::threads::{available_threads, record_threads_used, spawn, JoinHandle, ThreadPool};
 
 use std::time::Instant;
 
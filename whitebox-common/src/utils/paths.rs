@@ -0,0 +1,209 @@
+/////////////////////////////////////////////////////
+// Resolution of tool input and output file paths //
+/////////////////////////////////////////////////////
+
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, MAIN_SEPARATOR};
+
+/// Returns true if `file_name` is a URL, i.e. it begins with a scheme such as `https://`
+/// or `s3://`.
+pub fn is_url(file_name: &str) -> bool {
+    match file_name.find("://") {
+        Some(i) if i > 0 => {
+            let scheme = &file_name[..i];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if `file_name` names an in-memory dataset, i.e. it begins with `memory:`.
+/// In-memory datasets are held by the process rather than written to disk, e.g. to pass
+/// intermediate rasters between the steps of a pipeline.
+pub fn is_memory_dataset(file_name: &str) -> bool {
+    file_name.trim().to_lowercase().starts_with("memory:")
+}
+
+/// Returns true if `file_name` names a table in a PostGIS database, i.e. it begins with
+/// `postgis:` (e.g. `postgis:public.roads`). The database is given by the PostGIS connection
+/// string of the settings.
+pub fn is_postgis_dataset(file_name: &str) -> bool {
+    file_name.trim().to_lowercase().starts_with("postgis:")
+}
+
+/// Returns true if `file_name` names a SQLite database, i.e. it has a `.sqlite`, `.sqlite3`,
+/// or `.db` extension. Vector outputs with these extensions are written as SpatiaLite tables.
+pub fn is_sqlite_dataset(file_name: &str) -> bool {
+    match Path::new(file_name.trim()).extension() {
+        Some(ext) => {
+            let ext = ext.to_string_lossy().to_lowercase();
+            ext == "sqlite" || ext == "sqlite3" || ext == "db"
+        }
+        None => false,
+    }
+}
+
+/// Resolves a file name supplied as a tool parameter against the working directory.
+///
+/// URLs, in-memory and PostGIS dataset names and absolute paths are returned unchanged and a leading `~` is expanded to the
+/// user's home directory. Any other path, including a relative path that contains
+/// subdirectories (e.g. `tiles/dem.tif`), is joined to the working directory. Empty names
+/// are returned empty so that unspecified optional parameters remain recognizable.
+pub fn resolve_path(file_name: &str, working_directory: &str) -> String {
+    let file_name = file_name.trim();
+    if file_name.is_empty()
+        || is_url(file_name)
+        || is_memory_dataset(file_name)
+        || is_postgis_dataset(file_name)
+    {
+        return file_name.to_string();
+    }
+    let file_name = expand_home_dir(file_name);
+    if is_absolute(&file_name) || working_directory.is_empty() {
+        return file_name;
+    }
+    if working_directory.ends_with('/') || working_directory.ends_with(MAIN_SEPARATOR) {
+        format!("{}{}", working_directory, file_name)
+    } else {
+        format!("{}{}{}", working_directory, MAIN_SEPARATOR, file_name)
+    }
+}
+
+/// Resolves an input file name in the same way as `resolve_path` and returns an error if
+/// the resolved file does not exist. The error names both the file as supplied and the
+/// location it was resolved to. URLs, in-memory and PostGIS datasets and empty names are not
+/// checked.
+pub fn resolve_input_path(file_name: &str, working_directory: &str) -> Result<String, Error> {
+    let resolved = resolve_path(file_name, working_directory);
+    if resolved.is_empty()
+        || is_url(&resolved)
+        || is_memory_dataset(&resolved)
+        || is_postgis_dataset(&resolved)
+        || Path::new(&resolved).exists()
+    {
+        return Ok(resolved);
+    }
+    let supplied = file_name.trim();
+    let mut msg = if resolved == supplied {
+        format!("The input file {} does not exist.", resolved)
+    } else {
+        format!(
+            "The input file {} does not exist (resolved to {} using the working directory {}).",
+            supplied, resolved, working_directory
+        )
+    };
+    if !is_absolute(supplied) && Path::new(supplied).exists() {
+        msg.push_str(
+            " A file of that name exists relative to the current directory; relative paths are resolved against the working directory, so provide an absolute path or change the working directory.",
+        );
+    }
+    Err(Error::new(ErrorKind::NotFound, msg))
+}
+
+fn expand_home_dir(file_name: &str) -> String {
+    if file_name == "~" || file_name.starts_with("~/") || file_name.starts_with("~\\") {
+        if let Some(home) = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE")) {
+            let home = home.to_string_lossy().to_string();
+            if file_name.len() == 1 {
+                return home;
+            }
+            let home = home.trim_end_matches(['/', '\\']);
+            return format!("{}{}", home, &file_name[1..]);
+        }
+    }
+    file_name.to_string()
+}
+
+fn is_absolute(file_name: &str) -> bool {
+    let path = Path::new(file_name);
+    if path.is_absolute() || path.has_root() || file_name.starts_with('/') || file_name.starts_with('\\') {
+        return true;
+    }
+    // Windows drive letters, e.g. C:\data or C:/data
+    let bytes = file_name.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        is_memory_dataset, is_postgis_dataset, is_sqlite_dataset, is_url, resolve_input_path,
+        resolve_path,
+    };
+    use std::path::MAIN_SEPARATOR;
+
+    #[test]
+    fn test_urls_pass_through() {
+        assert!(is_url("https://example.com/dem.tif"));
+        assert!(is_url("s3://bucket/dem.tif"));
+        assert!(!is_url("dem.tif"));
+        assert!(!is_url("://dem.tif"));
+        assert_eq!(
+            resolve_path("https://example.com/dem.tif", "/data/"),
+            "https://example.com/dem.tif"
+        );
+    }
+
+    #[test]
+    fn test_memory_datasets_pass_through() {
+        assert!(is_memory_dataset("memory:breached"));
+        assert!(is_memory_dataset("Memory:breached.tif"));
+        assert!(!is_memory_dataset("memory.tif"));
+        assert_eq!(resolve_path("memory:breached", "/data/"), "memory:breached");
+        assert_eq!(
+            resolve_input_path("memory:breached", "/no/such/dir/").unwrap(),
+            "memory:breached"
+        );
+    }
+
+    #[test]
+    fn test_postgis_datasets_pass_through() {
+        assert!(is_postgis_dataset("postgis:public.roads"));
+        assert!(is_postgis_dataset("PostGIS:roads"));
+        assert!(!is_postgis_dataset("postgis.shp"));
+        assert_eq!(resolve_path("postgis:roads", "/data/"), "postgis:roads");
+        assert_eq!(
+            resolve_input_path("postgis:public.roads", "/no/such/dir/").unwrap(),
+            "postgis:public.roads"
+        );
+    }
+
+    #[test]
+    fn test_recognizes_sqlite_datasets() {
+        assert!(is_sqlite_dataset("reaches.sqlite"));
+        assert!(is_sqlite_dataset("/data/Catalog.DB"));
+        assert!(is_sqlite_dataset("s3://bucket/stats.sqlite3"));
+        assert!(!is_sqlite_dataset("reaches.shp"));
+        assert!(!is_sqlite_dataset("sqlite"));
+    }
+
+    #[test]
+    fn test_relative_paths_join_working_directory() {
+        assert_eq!(resolve_path("dem.tif", "/data/"), "/data/dem.tif");
+        assert_eq!(resolve_path("tiles/dem.tif", "/data/"), "/data/tiles/dem.tif");
+        assert_eq!(
+            resolve_path("dem.tif", "/data"),
+            format!("/data{}dem.tif", MAIN_SEPARATOR)
+        );
+        assert_eq!(resolve_path("dem.tif", ""), "dem.tif");
+        assert_eq!(resolve_path("", "/data/"), "");
+    }
+
+    #[test]
+    fn test_absolute_paths_unchanged() {
+        assert_eq!(resolve_path("/other/dem.tif", "/data/"), "/other/dem.tif");
+        assert_eq!(resolve_path("C:\\other\\dem.tif", "/data/"), "C:\\other\\dem.tif");
+    }
+
+    #[test]
+    fn test_missing_input_is_not_found() {
+        let err = resolve_input_path("no_such_file_here.tif", "/no/such/dir/").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("/no/such/dir/no_such_file_here.tif"));
+        assert_eq!(resolve_input_path("", "/no/such/dir/").unwrap(), "");
+    }
+}
@@ -0,0 +1,980 @@
+/////////////////////////////////////////////////
+// Reading and writing SQLite 3 database files //
+/////////////////////////////////////////////////
+
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, ErrorKind, SeekFrom};
+
+/// The size of the pages of the database, in bytes.
+const PAGE_SIZE: usize = 4096;
+/// The size of the database file header, which precedes the schema table on the first page.
+const HEADER_SIZE: usize = 100;
+const LEAF_TABLE_PAGE: u8 = 0x0D;
+const INTERIOR_TABLE_PAGE: u8 = 0x05;
+/// The SQLite version number recorded in the header of the files that are written.
+const SQLITE_VERSION_NUMBER: u32 = 3_037_000;
+
+/// A value stored in a column of a SQLite table.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// Writes a new SQLite 3 database file, i.e. one that can be opened by the `sqlite3` program and
+/// by any SQLite library, holding tables that are written in their entirety, one at a time.
+///
+/// Each table is written as a B-tree of 4096-byte pages, with the rows of the table given rowids
+/// 1, 2, 3, ..., in the order in which they are supplied. A column declared `INTEGER PRIMARY KEY`
+/// is an alias of the rowid and should be given a `Null` value. The tables have no indices, and
+/// so their `CREATE TABLE` statements must not declare any `UNIQUE` or non-rowid `PRIMARY KEY`
+/// constraints. The schema is written, completing the file, by `finish`.
+pub struct SqliteWriter {
+    writer: BufWriter<File>,
+    num_pages: u32,
+    /// The name, root page, and `CREATE TABLE` statement of each table.
+    tables: Vec<(String, u32, String)>,
+    application_id: u32,
+    user_version: u32,
+}
+
+impl SqliteWriter {
+    /// Creates a new, empty database file, replacing any existing file.
+    pub fn create(file_name: &str) -> Result<SqliteWriter, Error> {
+        let mut writer = BufWriter::new(File::create(file_name)?);
+        // the first page, which holds the header and the schema, is written last
+        writer.seek(SeekFrom::Start(PAGE_SIZE as u64))?;
+        Ok(SqliteWriter {
+            writer,
+            num_pages: 1,
+            tables: vec![],
+            application_id: 0,
+            user_version: 0,
+        })
+    }
+
+    /// Sets the application ID recorded in the header, which identifies the file format of
+    /// databases that are used as application files (e.g. 0x47504B47, 'GPKG', for GeoPackage).
+    pub fn set_application_id(&mut self, application_id: u32) {
+        self.application_id = application_id;
+    }
+
+    /// Sets the user version recorded in the header, which such formats use for their version.
+    pub fn set_user_version(&mut self, user_version: u32) {
+        self.user_version = user_version;
+    }
+
+    /// Writes a table, given its name, its `CREATE TABLE` statement, and its rows.
+    pub fn write_table<I>(&mut self, name: &str, create_sql: &str, rows: I) -> Result<(), Error>
+    where
+        I: IntoIterator<Item = Vec<SqlValue>>,
+    {
+        if self
+            .tables
+            .iter()
+            .any(|(n, _, _)| n.eq_ignore_ascii_case(name))
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The database already contains a table named {}.", name),
+            ));
+        }
+        let root = self.write_btree(rows.into_iter().map(|row| encode_record(&row)), 0)?;
+        let root_page = self.write_page(&root)?;
+        self.tables
+            .push((name.to_string(), root_page, create_sql.to_string()));
+        Ok(())
+    }
+
+    /// Writes the schema and header, completing the database.
+    pub fn finish(mut self) -> Result<(), Error> {
+        let tables = std::mem::take(&mut self.tables);
+        let records: Vec<Vec<u8>> = tables
+            .into_iter()
+            .map(|(name, root_page, sql)| {
+                encode_record(&[
+                    SqlValue::Text("table".to_string()),
+                    SqlValue::Text(name.clone()),
+                    SqlValue::Text(name),
+                    SqlValue::Integer(root_page as i64),
+                    SqlValue::Text(sql),
+                ])
+            })
+            .collect();
+        let mut first_page = self.write_btree(records, HEADER_SIZE)?;
+
+        let header = &mut first_page[..HEADER_SIZE];
+        header[..16].copy_from_slice(b"SQLite format 3\0");
+        header[16..18].copy_from_slice(&(PAGE_SIZE as u16).to_be_bytes());
+        header[18] = 1; // file format write version (legacy)
+        header[19] = 1; // file format read version (legacy)
+        header[20] = 0; // reserved space at the end of each page
+        header[21] = 64; // maximum embedded payload fraction
+        header[22] = 32; // minimum embedded payload fraction
+        header[23] = 32; // leaf payload fraction
+        header[24..28].copy_from_slice(&1u32.to_be_bytes()); // file change counter
+        header[28..32].copy_from_slice(&self.num_pages.to_be_bytes()); // database size, in pages
+        header[40..44].copy_from_slice(&1u32.to_be_bytes()); // schema cookie
+        header[44..48].copy_from_slice(&4u32.to_be_bytes()); // schema format number
+        header[56..60].copy_from_slice(&1u32.to_be_bytes()); // text encoding (UTF-8)
+        header[60..64].copy_from_slice(&self.user_version.to_be_bytes());
+        header[68..72].copy_from_slice(&self.application_id.to_be_bytes());
+        header[92..96].copy_from_slice(&1u32.to_be_bytes()); // version-valid-for number
+        header[96..100].copy_from_slice(&SQLITE_VERSION_NUMBER.to_be_bytes());
+
+        self.writer.seek(SeekFrom::Start(0))?;
+        self.writer.write_all(&first_page)?;
+        self.writer.flush()
+    }
+
+    /// Appends a page to the file, returning its (1-based) page number.
+    fn write_page(&mut self, page: &[u8]) -> Result<u32, Error> {
+        if self.num_pages == u32::MAX - 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The database exceeds the maximum size of a SQLite file.",
+            ));
+        }
+        self.writer.write_all(page)?;
+        self.num_pages += 1;
+        Ok(self.num_pages)
+    }
+
+    /// Encodes the cell of a table leaf page, writing any part of the record that does not fit
+    /// on the page to a chain of overflow pages.
+    fn leaf_cell(&mut self, rowid: i64, record: &[u8]) -> Result<Vec<u8>, Error> {
+        let local = local_payload_size(record.len(), PAGE_SIZE);
+        let mut cell = Vec::with_capacity(local + 22);
+        put_varint(&mut cell, record.len() as u64);
+        put_varint(&mut cell, rowid as u64);
+        cell.extend_from_slice(&record[..local]);
+        if local < record.len() {
+            cell.extend_from_slice(&(self.num_pages + 1).to_be_bytes());
+            let chunks: Vec<&[u8]> = record[local..].chunks(PAGE_SIZE - 4).collect();
+            for (i, chunk) in chunks.iter().enumerate() {
+                let mut page = vec![0u8; PAGE_SIZE];
+                if i + 1 < chunks.len() {
+                    page[..4].copy_from_slice(&(self.num_pages + 2).to_be_bytes());
+                }
+                page[4..4 + chunk.len()].copy_from_slice(chunk);
+                self.write_page(&page)?;
+            }
+        }
+        Ok(cell)
+    }
+
+    /// Writes the pages of a table B-tree holding the given records, with rowids 1, 2, 3, ...,
+    /// other than its root page, which is returned, with its content beginning at `root_offset`.
+    fn write_btree<I>(&mut self, records: I, root_offset: usize) -> Result<Vec<u8>, Error>
+    where
+        I: IntoIterator<Item = Vec<u8>>,
+    {
+        // the leaves are written as they fill, so that large tables are never held in memory
+        let mut cells: Vec<Vec<u8>> = vec![];
+        let mut used = 0usize;
+        let mut children = vec![];
+        let mut max_rowid = 0i64;
+        for record in records {
+            max_rowid += 1;
+            let cell = self.leaf_cell(max_rowid, &record)?;
+            if !cells.is_empty() && !fits(8, used + cell.len(), cells.len() + 1, 0) {
+                let page = self.write_page(&build_page(LEAF_TABLE_PAGE, &cells, None, 0))?;
+                children.push((page, max_rowid - 1));
+                cells.clear();
+                used = 0;
+            }
+            used += cell.len();
+            cells.push(cell);
+        }
+        if children.is_empty() && fits(8, used, cells.len(), root_offset) {
+            return Ok(build_page(LEAF_TABLE_PAGE, &cells, None, root_offset));
+        }
+        if !cells.is_empty() {
+            let page = self.write_page(&build_page(LEAF_TABLE_PAGE, &cells, None, 0))?;
+            children.push((page, max_rowid));
+        }
+        loop {
+            // each interior page points to its last child with its right-most pointer, and to
+            // the others with cells keyed by the largest rowid within them
+            let mut parents = vec![];
+            let mut cells: Vec<Vec<u8>> = vec![];
+            let mut used = 0usize;
+            let mut right: Option<(u32, i64)> = None;
+            for child in children {
+                if let Some((page, key)) = right {
+                    let mut cell = page.to_be_bytes().to_vec();
+                    put_varint(&mut cell, key as u64);
+                    if fits(12, used + cell.len(), cells.len() + 1, 0) {
+                        used += cell.len();
+                        cells.push(cell);
+                    } else {
+                        let parent = self.write_page(&build_page(
+                            INTERIOR_TABLE_PAGE,
+                            &cells,
+                            Some(page),
+                            0,
+                        ))?;
+                        parents.push((parent, key));
+                        cells.clear();
+                        used = 0;
+                    }
+                }
+                right = Some(child);
+            }
+            let (page, key) = right.unwrap();
+            // an interior root without cells is only valid on the first page, which can be
+            // left with one if the schema does not fit beside the file header
+            if parents.is_empty() && fits(12, used, cells.len(), root_offset) {
+                return Ok(build_page(
+                    INTERIOR_TABLE_PAGE,
+                    &cells,
+                    Some(page),
+                    root_offset,
+                ));
+            }
+            let parent =
+                self.write_page(&build_page(INTERIOR_TABLE_PAGE, &cells, Some(page), 0))?;
+            parents.push((parent, key));
+            children = parents;
+        }
+    }
+}
+
+/// Returns whether `num_cells` cells, of `cells_size` bytes in total, fit on a page whose
+/// `header_size`-byte page header begins at `offset`.
+fn fits(header_size: usize, cells_size: usize, num_cells: usize, offset: usize) -> bool {
+    offset + header_size + 2 * num_cells + cells_size <= PAGE_SIZE
+}
+
+/// Lays out a B-tree page, with the cell pointer array following the page header and the cells
+/// packed against the end of the page.
+fn build_page(page_type: u8, cells: &[Vec<u8>], right: Option<u32>, offset: usize) -> Vec<u8> {
+    let mut page = vec![0u8; PAGE_SIZE];
+    let header_size = if right.is_some() { 12 } else { 8 };
+    let mut content = PAGE_SIZE;
+    let mut pointer = offset + header_size;
+    for cell in cells {
+        content -= cell.len();
+        page[content..content + cell.len()].copy_from_slice(cell);
+        page[pointer..pointer + 2].copy_from_slice(&(content as u16).to_be_bytes());
+        pointer += 2;
+    }
+    page[offset] = page_type;
+    page[offset + 3..offset + 5].copy_from_slice(&(cells.len() as u16).to_be_bytes());
+    page[offset + 5..offset + 7].copy_from_slice(&(content as u16).to_be_bytes());
+    if let Some(right) = right {
+        page[offset + 8..offset + 12].copy_from_slice(&right.to_be_bytes());
+    }
+    page
+}
+
+/// Returns the number of bytes of a payload of `payload_size` bytes that are stored on a table
+/// leaf page, rather than on overflow pages, given the usable size of the pages (i.e. the page
+/// size less any space reserved at the end of each page).
+fn local_payload_size(payload_size: usize, usable_size: usize) -> usize {
+    let max_local = usable_size - 35;
+    if payload_size <= max_local {
+        return payload_size;
+    }
+    let min_local = (usable_size - 12) * 32 / 255 - 23;
+    let local = min_local + (payload_size - min_local) % (usable_size - 4);
+    if local <= max_local {
+        local
+    } else {
+        min_local
+    }
+}
+
+/// Encodes a variable-length integer in the big-endian format used by SQLite, which stores
+/// seven bits in each of the first eight bytes and eight bits in the ninth.
+fn put_varint(buf: &mut Vec<u8>, value: u64) {
+    if value > 0x00ff_ffff_ffff_ffff {
+        let mut bytes = [0u8; 9];
+        bytes[8] = value as u8;
+        let mut v = value >> 8;
+        for b in bytes[..8].iter_mut().rev() {
+            *b = (v & 0x7f) as u8 | 0x80;
+            v >>= 7;
+        }
+        buf.extend_from_slice(&bytes);
+        return;
+    }
+    let mut bytes = vec![(value & 0x7f) as u8];
+    let mut v = value >> 7;
+    while v > 0 {
+        bytes.push((v & 0x7f) as u8 | 0x80);
+        v >>= 7;
+    }
+    buf.extend(bytes.iter().rev());
+}
+
+fn varint_len(value: u64) -> usize {
+    let mut buf = vec![];
+    put_varint(&mut buf, value);
+    buf.len()
+}
+
+/// Encodes a row in the SQLite record format, i.e. a header of the serial types of the values,
+/// followed by the values themselves.
+fn encode_record(values: &[SqlValue]) -> Vec<u8> {
+    let mut types = vec![];
+    let mut body = vec![];
+    for value in values {
+        let serial_type = match value {
+            SqlValue::Null => 0,
+            SqlValue::Integer(0) => 8,
+            SqlValue::Integer(1) => 9,
+            SqlValue::Integer(v) => {
+                let (serial_type, num_bytes) = match *v {
+                    -0x80..=0x7f => (1, 1),
+                    -0x8000..=0x7fff => (2, 2),
+                    -0x80_0000..=0x7f_ffff => (3, 3),
+                    -0x8000_0000..=0x7fff_ffff => (4, 4),
+                    -0x8000_0000_0000..=0x7fff_ffff_ffff => (5, 6),
+                    _ => (6, 8),
+                };
+                body.extend_from_slice(&v.to_be_bytes()[8 - num_bytes..]);
+                serial_type
+            }
+            // SQLite has no representation of NaN, which it reads as NULL
+            SqlValue::Real(v) if v.is_nan() => 0,
+            SqlValue::Real(v) => {
+                body.extend_from_slice(&v.to_be_bytes());
+                7
+            }
+            SqlValue::Text(s) => {
+                body.extend_from_slice(s.as_bytes());
+                s.len() as u64 * 2 + 13
+            }
+            SqlValue::Blob(b) => {
+                body.extend_from_slice(b);
+                b.len() as u64 * 2 + 12
+            }
+        };
+        put_varint(&mut types, serial_type);
+    }
+    // the header size includes the varint that encodes it
+    let mut size_len = 1;
+    while varint_len((types.len() + size_len) as u64) > size_len {
+        size_len += 1;
+    }
+    let mut record = Vec::with_capacity(types.len() + size_len + body.len());
+    put_varint(&mut record, (types.len() + size_len) as u64);
+    record.extend_from_slice(&types);
+    record.extend_from_slice(&body);
+    record
+}
+
+fn corrupt(file_name: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("The SQLite database {} is malformed.", file_name),
+    )
+}
+
+/// Decodes a variable-length integer, advancing `pos` past it, or returns None if the buffer
+/// ends first.
+fn get_varint(buf: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut v = 0u64;
+    for i in 0..9 {
+        let b = *buf.get(*pos)?;
+        *pos += 1;
+        if i == 8 {
+            return Some((v << 8) | b as u64);
+        }
+        v = (v << 7) | (b & 0x7f) as u64;
+        if b < 0x80 {
+            break;
+        }
+    }
+    Some(v)
+}
+
+/// Decodes a row from the SQLite record format, or returns None if the record is truncated.
+fn decode_record(record: &[u8]) -> Option<Vec<SqlValue>> {
+    let mut pos = 0;
+    let header_size = get_varint(record, &mut pos)? as usize;
+    let mut types = vec![];
+    while pos < header_size {
+        types.push(get_varint(record, &mut pos)?);
+    }
+    pos = header_size;
+    let mut values = Vec::with_capacity(types.len());
+    for t in types {
+        let value = match t {
+            0 | 10 | 11 => SqlValue::Null,
+            8 => SqlValue::Integer(0),
+            9 => SqlValue::Integer(1),
+            1..=6 => {
+                let n = [0, 1, 2, 3, 4, 6, 8][t as usize];
+                let bytes = record.get(pos..pos + n)?;
+                let mut v = (bytes[0] as i8) as i64;
+                for b in &bytes[1..] {
+                    v = (v << 8) | *b as i64;
+                }
+                pos += n;
+                SqlValue::Integer(v)
+            }
+            7 => {
+                let bytes = record.get(pos..pos + 8)?;
+                pos += 8;
+                SqlValue::Real(f64::from_be_bytes(bytes.try_into().ok()?))
+            }
+            _ => {
+                let n = ((t - 12) / 2) as usize;
+                let bytes = record.get(pos..pos + n)?;
+                pos += n;
+                if t % 2 == 1 {
+                    SqlValue::Text(String::from_utf8_lossy(bytes).to_string())
+                } else {
+                    SqlValue::Blob(bytes.to_vec())
+                }
+            }
+        };
+        values.push(value);
+    }
+    Some(values)
+}
+
+/// Splits the column definitions of a `CREATE TABLE` statement on the commas between them,
+/// ignoring those within parentheses or quotes.
+fn split_definitions(sql: &str) -> Vec<String> {
+    let mut definitions = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    for c in sql.chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '[' => quote = Some(']'),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    definitions.push(current.trim().to_string());
+                    current.clear();
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        definitions.push(current.trim().to_string());
+    }
+    definitions
+}
+
+/// Removes the quotes from an SQL identifier.
+fn unquote_ident(name: &str) -> String {
+    let bytes = name.as_bytes();
+    if bytes.len() >= 2 {
+        match (bytes[0], bytes[bytes.len() - 1]) {
+            (q @ (b'"' | b'`' | b'\''), last) if last == q => {
+                let q = (q as char).to_string();
+                return name[1..name.len() - 1].replace(&q.repeat(2), &q);
+            }
+            (b'[', b']') => return name[1..name.len() - 1].to_string(),
+            _ => {}
+        }
+    }
+    name.to_string()
+}
+
+/// Splits a column definition into its (unquoted) column name and the remainder of the
+/// definition.
+fn split_column_name(definition: &str) -> (String, &str) {
+    let bytes = definition.as_bytes();
+    let end = match bytes.first() {
+        Some(&q @ (b'"' | b'`' | b'\'' | b'[')) => {
+            let close = if q == b'[' { b']' } else { q };
+            let mut i = 1;
+            while i < bytes.len() {
+                if bytes[i] == close {
+                    // doubled quotes within a quoted name are escapes
+                    if close != b']' && bytes.get(i + 1) == Some(&close) {
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                i += 1;
+            }
+            (i + 1).min(bytes.len())
+        }
+        _ => definition
+            .find(char::is_whitespace)
+            .unwrap_or(definition.len()),
+    };
+    (unquote_ident(&definition[..end]), &definition[end..])
+}
+
+/// Returns the upper-case words of a fragment of SQL, with any parentheses as separate words.
+fn sql_words(sql: &str) -> Vec<String> {
+    sql.replace('(', " ( ")
+        .replace(')', " ) ")
+        .split_whitespace()
+        .map(|w| w.to_uppercase())
+        .collect()
+}
+
+/// The words that end the declared type of a column, beginning its constraints.
+const COLUMN_CONSTRAINTS: [&str; 11] = [
+    "CONSTRAINT",
+    "PRIMARY",
+    "NOT",
+    "NULL",
+    "UNIQUE",
+    "CHECK",
+    "DEFAULT",
+    "COLLATE",
+    "REFERENCES",
+    "GENERATED",
+    "AS",
+];
+
+/// The columns of a table, as declared by its `CREATE TABLE` statement.
+struct TableSchema {
+    columns: Vec<String>,
+    /// Whether each column has REAL affinity, in which case SQLite stores integral
+    /// floating-point values as integers, which are read back as floating-point values.
+    real_affinity: Vec<bool>,
+    /// The index of the column that is an alias of the rowid (an `INTEGER PRIMARY KEY`).
+    rowid_alias: Option<usize>,
+    without_rowid: bool,
+}
+
+/// Parses the columns of a table from its `CREATE TABLE` statement.
+fn parse_columns(sql: &str) -> Option<TableSchema> {
+    let open = sql.find('(')?;
+    let close = sql.rfind(')')?;
+    if close < open {
+        return None;
+    }
+    let mut schema = TableSchema {
+        columns: vec![],
+        real_affinity: vec![],
+        rowid_alias: None,
+        without_rowid: sql_words(&sql[close + 1..])
+            .join(" ")
+            .contains("WITHOUT ROWID"),
+    };
+    let mut types = vec![];
+    for definition in split_definitions(&sql[open + 1..close]) {
+        let words = sql_words(&definition);
+        let first_word = words.first().map(|w| w.as_str()).unwrap_or("");
+        if ["CONSTRAINT", "PRIMARY", "UNIQUE", "CHECK", "FOREIGN"].contains(&first_word) {
+            // a table constraint, such as PRIMARY KEY (id), which may also declare the alias
+            let words = words.join(" ");
+            if let Some(i) = words.find("PRIMARY KEY (") {
+                let inner = words[i + 13..].split(')').next().unwrap_or("").trim();
+                if !inner.contains(',') && !inner.contains(" DESC") {
+                    let name = unquote_ident(inner.split_whitespace().next().unwrap_or(""));
+                    if let Some(j) = schema
+                        .columns
+                        .iter()
+                        .position(|c| c.eq_ignore_ascii_case(&name))
+                    {
+                        if types[j] == "INTEGER" {
+                            schema.rowid_alias = Some(j);
+                        }
+                    }
+                }
+            }
+            continue;
+        }
+        let (name, rest) = split_column_name(&definition);
+        let rest = sql_words(rest);
+        let declared_type = rest
+            .iter()
+            .take_while(|w| !COLUMN_CONSTRAINTS.contains(&w.as_str()))
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(" ");
+        let constraints = rest.join(" ");
+        if declared_type == "INTEGER"
+            && constraints.contains("PRIMARY KEY")
+            && !constraints.contains("PRIMARY KEY DESC")
+        {
+            schema.rowid_alias = Some(schema.columns.len());
+        }
+        // the rules by which SQLite determines the affinity of a column from its type
+        let real_affinity = !declared_type.contains("INT")
+            && !declared_type.contains("CHAR")
+            && !declared_type.contains("CLOB")
+            && !declared_type.contains("TEXT")
+            && !declared_type.contains("BLOB")
+            && (declared_type.contains("REAL")
+                || declared_type.contains("FLOA")
+                || declared_type.contains("DOUB"));
+        schema.columns.push(name);
+        schema.real_affinity.push(real_affinity);
+        types.push(declared_type);
+    }
+    Some(schema)
+}
+
+/// Reads the tables of a SQLite 3 database file, e.g. one written by the `sqlite3` program, by
+/// any SQLite library, or by a `SqliteWriter`. Pages are read from the file as they are needed,
+/// so that large databases need not be held in memory.
+///
+/// Only the rows of tables (not indices) can be read, in the order of their rowids. Columns
+/// that were added to a table after some of its rows were written, and so are missing from
+/// those rows, are read as `Null` rather than as their default values.
+pub struct SqliteReader {
+    file_name: String,
+    file: File,
+    page_size: usize,
+    usable_size: usize,
+    num_pages: u64,
+    /// The name, root page, and `CREATE TABLE` statement of each table.
+    tables: Vec<(String, u32, String)>,
+}
+
+impl SqliteReader {
+    /// Opens a database file and reads its schema.
+    pub fn open(file_name: &str) -> Result<SqliteReader, Error> {
+        let mut file = File::open(file_name)?;
+        let mut header = [0u8; HEADER_SIZE];
+        if file.read_exact(&mut header).is_err() || &header[..16] != b"SQLite format 3\0" {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{} is not a SQLite 3 database.", file_name),
+            ));
+        }
+        let page_size = match u16::from_be_bytes([header[16], header[17]]) {
+            1 => 65_536,
+            n => n as usize,
+        };
+        let reserved = header[20] as usize;
+        if page_size < 512 || !page_size.is_power_of_two() || page_size - reserved < 480 {
+            return Err(corrupt(file_name));
+        }
+        if u32::from_be_bytes(header[56..60].try_into().unwrap()) > 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "The SQLite database {} uses a UTF-16 text encoding, which is not supported.",
+                    file_name
+                ),
+            ));
+        }
+        let num_pages = file.metadata()?.len() / page_size as u64;
+        let mut reader = SqliteReader {
+            file_name: file_name.to_string(),
+            file,
+            page_size,
+            usable_size: page_size - reserved,
+            num_pages,
+            tables: vec![],
+        };
+        let mut tables = vec![];
+        reader.read_btree(1, 0, &mut |_, values| {
+            if let [SqlValue::Text(kind), SqlValue::Text(name), _, SqlValue::Integer(root), SqlValue::Text(sql), ..] =
+                &values[..]
+            {
+                if kind == "table" && *root > 0 {
+                    tables.push((name.clone(), *root as u32, sql.clone()));
+                }
+            }
+        })?;
+        reader.tables = tables;
+        Ok(reader)
+    }
+
+    /// Returns the names of the tables in the database.
+    pub fn table_names(&self) -> Vec<String> {
+        self.tables
+            .iter()
+            .map(|(name, _, _)| name.clone())
+            .collect()
+    }
+
+    /// Returns whether the database contains a table, whose name is matched case-insensitively,
+    /// as SQLite does.
+    pub fn has_table(&self, name: &str) -> bool {
+        self.tables
+            .iter()
+            .any(|(n, _, _)| n.eq_ignore_ascii_case(name))
+    }
+
+    fn find_table(&self, name: &str) -> Result<(u32, TableSchema), Error> {
+        let (_, root, sql) = self
+            .tables
+            .iter()
+            .find(|(n, _, _)| n.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::NotFound,
+                    format!(
+                        "The SQLite database {} has no table named {}.",
+                        self.file_name, name
+                    ),
+                )
+            })?;
+        let schema = parse_columns(sql).ok_or_else(|| corrupt(&self.file_name))?;
+        if schema.without_rowid {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "The table {} is a WITHOUT ROWID table, which is not supported.",
+                    name
+                ),
+            ));
+        }
+        Ok((*root, schema))
+    }
+
+    /// Returns the names of the columns of a table.
+    pub fn table_columns(&mut self, name: &str) -> Result<Vec<String>, Error> {
+        Ok(self.find_table(name)?.1.columns)
+    }
+
+    /// Reads the rows of a table, in order, calling `f` with the values of each, which are in
+    /// the order of the table's columns. The column that is an alias of the rowid, if there is
+    /// one, holds the rowid, and integers stored in columns of REAL affinity (e.g. `DOUBLE`)
+    /// are converted to `Real` values, as SQLite does.
+    pub fn read_rows<F>(&mut self, name: &str, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(Vec<SqlValue>),
+    {
+        let (root, schema) = self.find_table(name)?;
+        self.read_btree(root, 0, &mut |rowid, mut values| {
+            values.resize(schema.columns.len(), SqlValue::Null);
+            for (value, &real) in values.iter_mut().zip(&schema.real_affinity) {
+                if let (true, SqlValue::Integer(v)) = (real, &value) {
+                    *value = SqlValue::Real(*v as f64);
+                }
+            }
+            if let Some(i) = schema.rowid_alias {
+                values[i] = SqlValue::Integer(rowid);
+            }
+            f(values)
+        })
+    }
+
+    /// Reads all of the rows of a table.
+    pub fn read_table(&mut self, name: &str) -> Result<Vec<Vec<SqlValue>>, Error> {
+        let mut rows = vec![];
+        self.read_rows(name, |values| rows.push(values))?;
+        Ok(rows)
+    }
+
+    fn read_page(&mut self, page_num: u32) -> Result<Vec<u8>, Error> {
+        if page_num == 0 || page_num as u64 > self.num_pages {
+            return Err(corrupt(&self.file_name));
+        }
+        let mut page = vec![0u8; self.page_size];
+        self.file.seek(SeekFrom::Start(
+            (page_num as u64 - 1) * self.page_size as u64,
+        ))?;
+        self.file.read_exact(&mut page)?;
+        Ok(page)
+    }
+
+    /// Reads the (rowid, values) of each row of the table B-tree rooted at `page_num`, in order.
+    fn read_btree(
+        &mut self,
+        page_num: u32,
+        depth: usize,
+        f: &mut dyn FnMut(i64, Vec<SqlValue>),
+    ) -> Result<(), Error> {
+        // a B-tree of a valid database is never nearly this deep, so this catches cycles
+        if depth > 64 {
+            return Err(corrupt(&self.file_name));
+        }
+        let page = self.read_page(page_num)?;
+        let offset = if page_num == 1 { HEADER_SIZE } else { 0 };
+        let interior = match page[offset] {
+            INTERIOR_TABLE_PAGE => true,
+            LEAF_TABLE_PAGE => false,
+            _ => return Err(corrupt(&self.file_name)),
+        };
+        let num_cells = u16::from_be_bytes([page[offset + 3], page[offset + 4]]) as usize;
+        let header_size = if interior { 12 } else { 8 };
+        for i in 0..num_cells {
+            let p = offset + header_size + 2 * i;
+            let mut pos = match page.get(p..p + 2) {
+                Some(b) => u16::from_be_bytes([b[0], b[1]]) as usize,
+                None => return Err(corrupt(&self.file_name)),
+            };
+            if interior {
+                let child = match page.get(pos..pos + 4) {
+                    Some(b) => u32::from_be_bytes(b.try_into().unwrap()),
+                    None => return Err(corrupt(&self.file_name)),
+                };
+                self.read_btree(child, depth + 1, f)?;
+                continue;
+            }
+            let (payload_size, rowid) =
+                match (get_varint(&page, &mut pos), get_varint(&page, &mut pos)) {
+                    (Some(size), Some(rowid)) => (size as usize, rowid as i64),
+                    _ => return Err(corrupt(&self.file_name)),
+                };
+            let local = local_payload_size(payload_size, self.usable_size);
+            let mut record = match page.get(pos..pos + local) {
+                Some(b) => b.to_vec(),
+                None => return Err(corrupt(&self.file_name)),
+            };
+            if local < payload_size {
+                let mut next = match page.get(pos + local..pos + local + 4) {
+                    Some(b) => u32::from_be_bytes(b.try_into().unwrap()),
+                    None => return Err(corrupt(&self.file_name)),
+                };
+                while record.len() < payload_size {
+                    let overflow = self.read_page(next)?;
+                    let n = (payload_size - record.len()).min(self.usable_size - 4);
+                    record.extend_from_slice(&overflow[4..4 + n]);
+                    next = u32::from_be_bytes(overflow[..4].try_into().unwrap());
+                }
+            }
+            match decode_record(&record) {
+                Some(values) => f(rowid, values),
+                None => return Err(corrupt(&self.file_name)),
+            }
+        }
+        if interior {
+            let right = u32::from_be_bytes(page[offset + 8..offset + 12].try_into().unwrap());
+            self.read_btree(right, depth + 1, f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        decode_record, encode_record, get_varint, parse_columns, put_varint, SqlValue,
+        SqliteReader, SqliteWriter, PAGE_SIZE,
+    };
+    use std::fs;
+
+    #[test]
+    fn test_encodes_varints() {
+        let mut buf = vec![];
+        put_varint(&mut buf, 0x7f);
+        assert_eq!(buf, vec![0x7f]);
+        buf.clear();
+        put_varint(&mut buf, 0x80);
+        assert_eq!(buf, vec![0x81, 0x00]);
+        for v in [0u64, 240, 2287, 67823, 1 << 35, 1 << 56, u64::MAX] {
+            buf.clear();
+            put_varint(&mut buf, v);
+            assert_eq!(get_varint(&buf, &mut 0), Some(v));
+        }
+        buf.clear();
+        put_varint(&mut buf, u64::MAX);
+        assert_eq!(buf.len(), 9);
+    }
+
+    #[test]
+    fn test_encodes_records() {
+        let values = vec![
+            SqlValue::Null,
+            SqlValue::Integer(0),
+            SqlValue::Integer(1),
+            SqlValue::Integer(-200),
+            SqlValue::Integer(1 << 40),
+            SqlValue::Integer(i64::MIN),
+            SqlValue::Real(2.5),
+            SqlValue::Text("héllo".to_string()),
+            SqlValue::Blob(vec![0, 1, 2]),
+        ];
+        let record = encode_record(&values);
+        assert_eq!(&record[..3], &[10, 0, 8]);
+        assert_eq!(decode_record(&record), Some(values));
+        assert_eq!(decode_record(&record[..record.len() - 1]), None);
+    }
+
+    #[test]
+    fn test_parses_columns() {
+        let schema = parse_columns(
+            "CREATE TABLE \"t 1\" (\"fid\" INTEGER PRIMARY KEY AUTOINCREMENT, [a b] TEXT, c DOUBLE PRECISION DEFAULT (1.0), 'd' REAL, e FLOATING POINT, CONSTRAINT u UNIQUE (c, d))",
+        )
+        .unwrap();
+        assert_eq!(schema.columns, vec!["fid", "a b", "c", "d", "e"]);
+        assert_eq!(schema.real_affinity, vec![false, false, true, true, false]);
+        assert_eq!(schema.rowid_alias, Some(0));
+        assert!(!schema.without_rowid);
+        let schema =
+            parse_columns("CREATE TABLE t (a TEXT, id integer, PRIMARY KEY (id))").unwrap();
+        assert_eq!(schema.columns, vec!["a", "id"]);
+        assert_eq!(schema.rowid_alias, Some(1));
+        let schema =
+            parse_columns("CREATE TABLE t (id INT PRIMARY KEY, v BLOB) WITHOUT ROWID").unwrap();
+        assert_eq!(schema.rowid_alias, None);
+        assert!(schema.without_rowid);
+    }
+
+    #[test]
+    fn test_writes_database() {
+        let file_name = std::env::temp_dir()
+            .join(format!("wbt_sqlite_test_{}.sqlite", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let rows: Vec<Vec<SqlValue>> = (0..5000)
+            .map(|i| {
+                vec![
+                    SqlValue::Null,
+                    SqlValue::Text(format!("feature {}", i)),
+                    SqlValue::Real(i as f64 * 0.5),
+                    // every hundredth row spills onto overflow pages
+                    SqlValue::Blob(vec![
+                        (i % 251) as u8;
+                        if i % 100 == 0 { 10_000 } else { 20 }
+                    ]),
+                ]
+            })
+            .collect();
+        let mut writer = SqliteWriter::create(&file_name).unwrap();
+        writer.set_application_id(0x47504B47);
+        writer
+            .write_table("empty", "CREATE TABLE empty (a TEXT)", vec![])
+            .unwrap();
+        let sql = "CREATE TABLE data (id INTEGER PRIMARY KEY, name TEXT, value REAL, b BLOB)";
+        writer.write_table("data", sql, rows.clone()).unwrap();
+        assert!(writer
+            .write_table("DATA", sql, Vec::<Vec<SqlValue>>::new())
+            .is_err());
+        writer.finish().unwrap();
+
+        let db = fs::read(&file_name).unwrap();
+        assert_eq!(&db[..16], b"SQLite format 3\0");
+        let num_pages = u32::from_be_bytes(db[28..32].try_into().unwrap()) as usize;
+        assert_eq!(num_pages * PAGE_SIZE, db.len());
+
+        assert_eq!(
+            u32::from_be_bytes(db[68..72].try_into().unwrap()),
+            0x47504B47
+        );
+
+        let mut reader = SqliteReader::open(&file_name).unwrap();
+        fs::remove_file(&file_name).unwrap();
+        assert_eq!(reader.table_names(), vec!["empty", "data"]);
+        assert!(reader.has_table("Data"));
+        assert_eq!(
+            reader.table_columns("data").unwrap(),
+            vec!["id", "name", "value", "b"]
+        );
+        assert!(reader.read_table("empty").unwrap().is_empty());
+        assert!(reader.read_table("missing").is_err());
+        let read = reader.read_table("data").unwrap();
+        assert_eq!(read.len(), rows.len());
+        for (i, mut values) in read.into_iter().enumerate() {
+            // the rowid alias is read as the rowid
+            assert_eq!(values[0], SqlValue::Integer(i as i64 + 1));
+            values[0] = SqlValue::Null;
+            assert_eq!(values, rows[i]);
+        }
+    }
+}
@@ -0,0 +1,277 @@
+/*
+NOTES: Tools size their worker threads with a ThreadPool, rather than each querying the number
+of processors and applying the max_procs setting itself, such that the setting, including a
+value given for a single run with `--run --max_procs=n`, is honoured consistently. The number
+of available processors is that reported by the operating system for this process, and so it
+respects the CPU affinity and cgroup quotas with which cluster schedulers (e.g. SLURM) confine
+a job to its allocated cores.
+
+When the numa_pinning setting is enabled and the system has more than one NUMA node, threads
+spawned through a pool are pinned to the processors of a node, with the threads divided evenly
+among the nodes. A pinned thread stays local to the memory that it first touches, which
+benefits the large-memory hydrological tools that use it. Pinning is only supported on Linux,
+where the topology is read from /sys/devices/system/node; elsewhere threads are never pinned.
+
+The largest number of threads requested by a run is recorded, such that it can be reported
+once the run ends. The record is kept by the thread running the tool, within a call to
+`record_threads_used`, rather than for the whole process, and so runs that are executed
+concurrently, e.g. by the server, each report their own threads.
+
+wasm32 builds have no threads. There, `spawn` runs a worker on the calling thread before
+returning, which suits the tools' pattern of spawning workers that send their results over a
+channel and then receiving the results, and the available processors number one.
+*/
+
+use crate::configs::{get_configs, Configs};
+use std::cell::Cell;
+use std::fs;
+use std::thread;
+
+thread_local! {
+    // the largest number of threads requested by the run executing on this thread
+    static THREADS_USED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Sizes, and optionally pins, the worker threads of a tool.
+///
+/// ```ignore
+/// let configs = whitebox_common::configs::get_configs()?;
+/// let num_procs = ThreadPool::new(&configs).num_threads() as isize;
+/// ```
+#[derive(Clone, Debug)]
+pub struct ThreadPool {
+    num_threads: usize,
+    numa_nodes: Vec<Vec<usize>>,
+}
+
+impl ThreadPool {
+    /// Creates a pool using the available processors, limited by the max_procs setting, and
+    /// pinning its threads to NUMA nodes if the numa_pinning setting is enabled.
+    pub fn new(configs: &Configs) -> ThreadPool {
+        let mut num_threads = available_threads();
+        if configs.max_procs > 0 && (configs.max_procs as usize) < num_threads {
+            num_threads = configs.max_procs as usize;
+        }
+        let numa_nodes = if configs.numa_pinning {
+            numa_nodes()
+        } else {
+            vec![]
+        };
+        ThreadPool {
+            num_threads,
+            numa_nodes: if numa_nodes.len() > 1 {
+                numa_nodes
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    /// Creates a pool from the current settings, or using all of the available processors if
+    /// the settings can't be read.
+    pub fn from_settings() -> ThreadPool {
+        match get_configs() {
+            Ok(configs) => ThreadPool::new(&configs),
+            Err(_) => ThreadPool::new(&Configs::new()),
+        }
+    }
+
+    /// Limits the number of threads, e.g. to the number of items of work, with at least one.
+    pub fn max_threads(mut self, n: usize) -> ThreadPool {
+        self.num_threads = self.num_threads.min(n).max(1);
+        self
+    }
+
+    /// Returns the number of threads to use, recording it as used by the current run.
+    pub fn num_threads(&self) -> usize {
+        THREADS_USED.with(|used| used.set(used.get().max(self.num_threads)));
+        self.num_threads
+    }
+
+    /// Returns the number of NUMA nodes to which threads are pinned, or 0 if they aren't.
+    pub fn numa_node_count(&self) -> usize {
+        self.numa_nodes.len()
+    }
+
+    /// Spawns the thread numbered `tid` (from 0 to `num_threads() - 1`), pinning it to the
+    /// processors of a NUMA node if the pool pins its threads.
+    pub fn spawn<F, T>(&self, tid: usize, f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        if self.numa_nodes.is_empty() {
+            return spawn(f);
+        }
+        let node = tid * self.numa_nodes.len() / self.num_threads.max(tid + 1);
+        let cpus = self.numa_nodes[node.min(self.numa_nodes.len() - 1)].clone();
+        spawn(move || {
+            pin_current_thread(&cpus);
+            f()
+        })
+    }
+}
+
+/// The handle of a worker spawned with `spawn`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type JoinHandle<T> = thread::JoinHandle<T>;
+
+/// The handle of a worker spawned with `spawn`, which in wasm32 builds holds its result.
+#[cfg(target_arch = "wasm32")]
+pub struct JoinHandle<T>(T);
+
+#[cfg(target_arch = "wasm32")]
+impl<T> JoinHandle<T> {
+    /// Returns the result of the worker.
+    pub fn join(self) -> thread::Result<T> {
+        Ok(self.0)
+    }
+}
+
+/// Spawns a worker thread, or in wasm32 builds, which have no threads, runs the worker on the
+/// calling thread.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    thread::spawn(f)
+}
+
+/// Spawns a worker thread, or in wasm32 builds, which have no threads, runs the worker on the
+/// calling thread.
+#[cfg(target_arch = "wasm32")]
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    JoinHandle(f())
+}
+
+/// Returns the number of processors available to this process.
+pub fn available_threads() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Calls `f`, a run of a tool, and returns its result along with the largest number of threads
+/// that it requested from the thread on which it ran.
+pub fn record_threads_used<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let outer = THREADS_USED.with(|used| used.replace(0));
+    let result = f();
+    let threads_used = THREADS_USED.with(|used| used.replace(outer));
+    (result, threads_used)
+}
+
+/// Returns the processors of each NUMA node, or an empty list if the topology is unknown.
+fn numa_nodes() -> Vec<Vec<usize>> {
+    let mut nodes = vec![];
+    if let Ok(entries) = fs::read_dir("/sys/devices/system/node") {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(id) = name
+                .strip_prefix("node")
+                .and_then(|n| n.parse::<usize>().ok())
+            {
+                if let Ok(list) = fs::read_to_string(entry.path().join("cpulist")) {
+                    let cpus = parse_cpu_list(&list);
+                    if !cpus.is_empty() {
+                        nodes.push((id, cpus));
+                    }
+                }
+            }
+        }
+    }
+    nodes.sort();
+    nodes.into_iter().map(|(_, cpus)| cpus).collect()
+}
+
+/// Parses a Linux CPU list, e.g. '0-3,8-11,16'.
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = vec![];
+    for range in list.trim().split(',').filter(|r| !r.is_empty()) {
+        let mut bounds = range.splitn(2, '-');
+        let first = bounds.next().and_then(|v| v.trim().parse::<usize>().ok());
+        let last = match bounds.next() {
+            Some(v) => v.trim().parse::<usize>().ok(),
+            None => first,
+        };
+        if let (Some(first), Some(last)) = (first, last) {
+            cpus.extend(first..=last);
+        }
+    }
+    cpus
+}
+
+#[cfg(target_os = "linux")]
+fn pin_current_thread(cpus: &[usize]) -> bool {
+    extern "C" {
+        fn sched_setaffinity(pid: i32, cpusetsize: usize, mask: *const u64) -> i32;
+    }
+    // a cpu_set_t of 1024 processors
+    let mut mask = [0u64; 16];
+    for &cpu in cpus.iter().filter(|&&cpu| cpu < 1024) {
+        mask[cpu / 64] |= 1u64 << (cpu % 64);
+    }
+    // A pid of 0 is the calling thread. A failure leaves the thread unpinned, which is harmless.
+    unsafe { sched_setaffinity(0, std::mem::size_of_val(&mask), mask.as_ptr()) == 0 }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_current_thread(_cpus: &[usize]) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,8-9,16\n"), vec![0, 1, 2, 3, 8, 9, 16]);
+        assert_eq!(parse_cpu_list("5"), vec![5]);
+        assert!(parse_cpu_list("").is_empty());
+    }
+
+    #[test]
+    fn test_thread_pool_limits() {
+        let mut configs = Configs::new();
+        configs.max_procs = 1;
+        let pool = ThreadPool::new(&configs);
+        assert_eq!(pool.num_threads(), 1);
+        configs.max_procs = -1;
+        let pool = ThreadPool::new(&configs);
+        assert_eq!(pool.num_threads(), available_threads());
+        assert_eq!(pool.max_threads(0).num_threads(), 1);
+        let handle = ThreadPool::new(&configs).spawn(0, || 2 + 2);
+        assert_eq!(handle.join().unwrap(), 4);
+        assert_eq!(spawn(|| 3 * 3).join().unwrap(), 9);
+    }
+
+    #[test]
+    fn test_record_threads_used() {
+        let mut configs = Configs::new();
+        configs.max_procs = 1;
+        let (value, used) = record_threads_used(|| {
+            let (_, inner_used) =
+                record_threads_used(|| ThreadPool::new(&configs).max_threads(1).num_threads());
+            assert_eq!(inner_used, 1);
+            7
+        });
+        assert_eq!(value, 7);
+        assert_eq!(used, 0);
+
+        // a run on another thread is recorded separately
+        let (_, used) = record_threads_used(|| {
+            thread::spawn(|| {
+                record_threads_used(|| ThreadPool::new(&Configs::new()).num_threads()).1
+            })
+            .join()
+            .unwrap()
+        });
+        assert_eq!(used, 0);
+    }
+}
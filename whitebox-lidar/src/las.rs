@@ -10,13 +10,16 @@ License: MIT
 extern crate brotli;
 extern crate las;
 use super::header::LasHeader;
+use super::lidar_provider::lidar_provider;
 use super::point_data::{ ColourData, PointData, WaveformPacket };
 use super::vlr::Vlr;
 use super::zlidar_compression::{ZlidarCompression};
 use whitebox_raster::geotiff::geokeys::GeoKeys;
 use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
 use whitebox_common::structures::{ BoundingBox, Point3D };
-use whitebox_common::utils::{ ByteOrderReader, Endianness };
+use whitebox_common::utils::{
+    is_url, open_input, read_input, time_io, write_atomically, ByteOrderReader, Endianness,
+};
 use byteorder::{ LittleEndian, WriteBytesExt };
 use chrono::prelude::*;
 use core::slice;
@@ -49,6 +52,7 @@ use las::Write as OtherWrite;
 use las::Writer as OtherWriter;
 use las::raw::point::ScanAngle;
 use las::raw::vlr::RecordLength;
+use whitebox_common::errors::ErrorCause;
 
 #[derive(Default, Clone)]
 pub struct LasFile {
@@ -101,7 +105,13 @@ impl LasFile {
         };
         lf.file_mode = file_mode.to_lowercase();
         if lf.file_mode == "r" || lf.file_mode == "rh" {
-            lf.read()?;
+            if let Some(provider) = lidar_provider(file_name) {
+                let mut lf = provider.read(file_name)?;
+                lf.file_name = file_name.to_string();
+                lf.file_mode = "r".to_string();
+                return Ok(lf);
+            }
+            time_io(|| lf.read())?;
         } else {
             lf.file_mode = "w".to_string();
         }
@@ -528,6 +538,7 @@ impl LasFile {
             return self.read_laz_data();
         }
         let buffer = match self.file_name.to_lowercase().ends_with(".zip") {
+            false if is_url(&self.file_name) => read_input(&self.file_name)?,
             false => {
                 let mut f = File::open(&self.file_name).expect("Error opening LAS file.");
                 let metadata = fs::metadata(&self.file_name)?;
@@ -569,7 +580,7 @@ impl LasFile {
                 buffer
             }
             true => {
-                let file = File::open(&self.file_name)?;
+                let file = open_input(&self.file_name)?;
                 let mut zip = (zip::ZipArchive::new(file))?;
                 let mut f = zip.by_index(0).unwrap();
                 if !f.name().to_lowercase().ends_with(".las") {
@@ -577,8 +588,10 @@ impl LasFile {
                 "The data file contained within zipped archive does not have the proper 'las' extension."));
                 }
                 match f.compression() {
-                    CompressionMethod::Stored | CompressionMethod::Deflated | CompressionMethod::Bzip2 => (),
-                    _ => return Err(Error::new(ErrorKind::InvalidData,
+                    CompressionMethod::Stored | CompressionMethod::Deflated => (),
+                    #[cfg(not(target_arch = "wasm32"))]
+                    CompressionMethod::Bzip2 => (),
+                    _ => return Err(ErrorCause::UnsupportedFormat.error(
                     "Either the file is formatted incorrectly or it is an unsupported compression type.")),
                 }
                 let file_size: usize = f.size() as usize;
@@ -617,7 +630,7 @@ impl LasFile {
                 || self.header.version_minor > 5
             {
                 // There's something very wrong. Throw an error.
-                return Err(Error::new(ErrorKind::Other, format!("Error reading: {}\nIncorrect file version {}.{}\nEither the file is formatted incorrectly or it is an unsupported LAS version.", self.file_name, self.header.version_major, self.header.version_minor)));
+                return Err(ErrorCause::UnsupportedFormat.error(format!("Error reading: {}\nIncorrect file version {}.{}\nEither the file is formatted incorrectly or it is an unsupported LAS version.", self.file_name, self.header.version_major, self.header.version_minor)));
             }
             self.header.project_id_used = false;
         }
@@ -628,7 +641,7 @@ impl LasFile {
         bor.seek(0);
         self.header.file_signature = bor.read_utf8(4);
         if self.header.file_signature != "LASF" {
-            return Err(Error::new(ErrorKind::Other, format!("Error reading: {}\nIncorrect LAS file signature: {}.\nEither the file is formatted incorrectly or it is an unsupported LAS version.", self.file_name, self.header.file_signature)));
+            return Err(ErrorCause::UnsupportedFormat.error(format!("Error reading: {}\nIncorrect LAS file signature: {}.\nEither the file is formatted incorrectly or it is an unsupported LAS version.", self.file_name, self.header.file_signature)));
         }
         self.header.file_source_id = bor.read_u16()?;
         let ge_val = bor.read_u16()?;
@@ -1176,7 +1189,12 @@ impl LasFile {
         // At present, this uses the laz crate via the las-rs crate to convert into a WBT LasFile.
         // This doesn't seem like the most efficient way of doing this, and in the future I might
         // like to go via the crates directly.
-        let mut reader = Reader::from_path(&self.file_name).expect("Error reading LAZ file.");
+        let mut reader = if is_url(&self.file_name) {
+            Reader::new(Cursor::new(read_input(&self.file_name)?))
+        } else {
+            Reader::from_path(&self.file_name)
+        }
+        .expect("Error reading LAZ file.");
         let header = reader.header();
         let raw = header.clone().into_raw().unwrap();
 
@@ -1189,7 +1207,7 @@ impl LasFile {
             || self.header.version_minor > 5
         {
                 // There's something very wrong. Throw an error.
-                return Err(Error::new(ErrorKind::Other, format!("Error reading: {}\nIncorrect file version {}.{}\nEither the file is formatted incorrectly or it is an unsupported LAS version.", self.file_name, self.header.version_major, self.header.version_minor)));
+                return Err(ErrorCause::UnsupportedFormat.error(format!("Error reading: {}\nIncorrect file version {}.{}\nEither the file is formatted incorrectly or it is an unsupported LAS version.", self.file_name, self.header.version_major, self.header.version_minor)));
         }
 
         self.header.file_signature = str::from_utf8(&raw.file_signature).unwrap().to_owned();
@@ -1421,29 +1439,35 @@ impl LasFile {
     }
 
     pub fn read_zlidar_data(&mut self) -> Result<(), Error> {
-        let mut f = File::open(&self.file_name).expect("Error opening LAS file.");
-        let metadata = fs::metadata(&self.file_name)?;
-        let file_size: usize = metadata.len() as usize;
-
-        let mut buffer = vec![0; file_size];
-        if file_size < 1024 * 1024 * 500 {
-            // 2147483646 is the actual maximum file read on Mac
-            f.read(&mut buffer)?;
+        let buffer = if is_url(&self.file_name) {
+            read_input(&self.file_name)?
         } else {
-            let block_size = 1024 * 1024 * 500;
-            let mut start_byte = 0usize;
-            let mut end_byte = block_size;
-            let mut bytes_read = 0;
-            while bytes_read < file_size {
-                f.read(&mut buffer[start_byte..end_byte])?;
-                start_byte += block_size;
-                end_byte += block_size;
-                if end_byte > file_size {
-                    end_byte = file_size;
+            let mut f = File::open(&self.file_name).expect("Error opening LAS file.");
+            let metadata = fs::metadata(&self.file_name)?;
+            let file_size: usize = metadata.len() as usize;
+
+            let mut buffer = vec![0; file_size];
+            if file_size < 1024 * 1024 * 500 {
+                // 2147483646 is the actual maximum file read on Mac
+                f.read(&mut buffer)?;
+            } else {
+                let block_size = 1024 * 1024 * 500;
+                let mut start_byte = 0usize;
+                let mut end_byte = block_size;
+                let mut bytes_read = 0;
+                while bytes_read < file_size {
+                    f.read(&mut buffer[start_byte..end_byte])?;
+                    start_byte += block_size;
+                    end_byte += block_size;
+                    if end_byte > file_size {
+                        end_byte = file_size;
+                    }
+                    bytes_read += block_size;
                 }
-                bytes_read += block_size;
             }
-        }
+            buffer
+        };
+        let file_size = buffer.len();
 
         if buffer.len() < 375 {
             // The buffer is less than the header size. This is a sign
@@ -1469,7 +1493,7 @@ impl LasFile {
                 || self.header.version_minor > 5
             {
                 // There's something very wrong. Throw an error.
-                return Err(Error::new(ErrorKind::Other, format!("Error reading: {}\nIncorrect file version {}.{}\nEither the file is formatted incorrectly or it is an unsupported LAS version.", self.file_name, self.header.version_major, self.header.version_minor)));
+                return Err(ErrorCause::UnsupportedFormat.error(format!("Error reading: {}\nIncorrect file version {}.{}\nEither the file is formatted incorrectly or it is an unsupported LAS version.", self.file_name, self.header.version_major, self.header.version_minor)));
             }
             self.header.project_id_used = false;
         }
@@ -1480,7 +1504,7 @@ impl LasFile {
         bor.seek(0);
         self.header.file_signature = bor.read_utf8(4);
         if self.header.file_signature != "ZLDR" {
-            return Err(Error::new(ErrorKind::Other, format!("Error reading: {}\nIncorrect zlidar file signature: {}.\nEither the file is formatted incorrectly or it is an unsupported zlidar version.", self.file_name, self.header.file_signature)));
+            return Err(ErrorCause::UnsupportedFormat.error(format!("Error reading: {}\nIncorrect zlidar file signature: {}.\nEither the file is formatted incorrectly or it is an unsupported zlidar version.", self.file_name, self.header.file_signature)));
         }
         self.header.file_source_id = bor.read_u16()?;
         let ge_val = bor.read_u16()?;
@@ -1638,8 +1662,7 @@ impl LasFile {
             self.compression = match compression_method {
                 0 => { ZlidarCompression::Deflate { level: compression_level } },
                 1 => { ZlidarCompression::Brotli { level: compression_level } },
-                _ => {return Err(Error::new(
-                    ErrorKind::Other,
+                _ => {return Err(ErrorCause::UnsupportedFormat.error(
                     "Unsupported compression method.",
                 ));}
             };
@@ -2481,8 +2504,7 @@ impl LasFile {
                     block_bytes = 4u64 + 20u64 * num_fields as u64;
                     compression_method = bor.read_u8().expect("Error while reading byte data.");
                     if compression_method != 0 {
-                        return Err(Error::new(
-                            ErrorKind::Other,
+                        return Err(ErrorCause::UnsupportedFormat.error(
                             "Unsupported compression method.",
                         ));
                     }
@@ -2493,8 +2515,7 @@ impl LasFile {
                     if !(major_version == 0 && minor_version <= 1)
                         && !(major_version == 1 && minor_version == 0)
                     {
-                        return Err(Error::new(
-                            ErrorKind::Other,
+                        return Err(ErrorCause::UnsupportedFormat.error(
                             format!(
                                 "Unsupported ZLidar version {}.{}.",
                                 major_version, minor_version
@@ -2886,6 +2907,23 @@ impl LasFile {
             self.header.z_scale_factor = dec;
         }
 
+        if let Some(provider) = lidar_provider(&self.file_name) {
+            return provider.write(&self.file_name, self);
+        }
+
+        // write to a temporary file that is moved into place once complete
+        let file_name = self.file_name.clone();
+        let result = time_io(|| {
+            write_atomically(&file_name, |temp_file| {
+                self.file_name = temp_file.to_string();
+                self.write_file()
+            })
+        });
+        self.file_name = file_name;
+        result
+    }
+
+    fn write_file(&mut self) -> Result<(), Error> {
         if !self.file_name.to_lowercase().ends_with(".zip")
             && !self.file_name.to_lowercase().ends_with(".zlidar")
         {
@@ -3043,7 +3081,7 @@ impl LasFile {
                 3u8
             }
             _ => {
-                return Err(Error::new(ErrorKind::Other, "Unsupported point format"));
+                return Err(ErrorCause::UnsupportedFormat.error("Unsupported point format"));
             }
         };
 
@@ -3400,7 +3438,7 @@ impl LasFile {
                 }
             }
             _ => {
-                return Err(Error::new(ErrorKind::Other, "Unsupported point format"));
+                return Err(ErrorCause::UnsupportedFormat.error("Unsupported point format"));
             }
         }
 
@@ -3645,7 +3683,7 @@ impl LasFile {
                 3u8
             }
             _ => {
-                return Err(Error::new(ErrorKind::Other, "Unsupported point format"));
+                return Err(ErrorCause::UnsupportedFormat.error("Unsupported point format"));
             }
         };
 
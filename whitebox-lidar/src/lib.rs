@@ -9,6 +9,7 @@ License: MIT
 // private sub-module defined in other files
 mod header;
 mod las;
+mod lidar_provider;
 mod point_data;
 mod vlr;
 mod zlidar_compression;
@@ -31,6 +32,9 @@ pub use self::las::PointRecord6;
 pub use self::las::PointRecord7;
 pub use self::las::PointRecord8;
 pub use self::las::PointRecord9;
+pub use self::lidar_provider::{
+    register_lidar_provider, unregister_lidar_provider, LidarProvider, MemoryLidarProvider,
+};
 pub use self::point_data::convert_class_val_to_class_string;
 pub use self::point_data::ColourData;
 pub use self::point_data::PointData;
@@ -0,0 +1,142 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: LiDAR point clouds are read from and written to files by default. A `LidarProvider`
+supplies them from somewhere else instead, in the same way as the `RasterProvider` of the
+whitebox_raster crate does for rasters: a provider registered for a URL scheme (e.g. `app`)
+handles every point cloud whose name begins with that scheme (e.g. `app://tile.las`), and tools
+read and write such point clouds like any other. This is how LiDAR data are supplied to the
+wasm32 build, which has no file system.
+
+Providers exchange whole `LasFile`s, i.e. their headers, VLRs and point records. A point cloud
+read with the 'rh' (read header) file mode is read in full.
+*/
+
+use super::LasFile;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+use std::sync::{Arc, Mutex};
+use whitebox_common::utils::is_url;
+
+/// A source and destination of LiDAR point clouds other than files.
+pub trait LidarProvider: Send + Sync {
+    /// Reads the point cloud named `file_name`.
+    fn read(&self, file_name: &str) -> Result<LasFile, Error>;
+
+    /// Writes a point cloud, replacing any existing point cloud of the same name.
+    fn write(&self, file_name: &str, lidar: &LasFile) -> Result<(), Error>;
+
+    /// Returns true if a point cloud named `file_name` exists.
+    fn exists(&self, file_name: &str) -> bool;
+}
+
+/// Holds point clouds in memory. An application registers it for a scheme with
+/// `register_lidar_provider`, keeping a clone of the `Arc` to add its inputs and take the
+/// outputs of tools.
+#[derive(Default)]
+pub struct MemoryLidarProvider {
+    point_clouds: Mutex<HashMap<String, LasFile>>,
+}
+
+impl LidarProvider for MemoryLidarProvider {
+    fn read(&self, file_name: &str) -> Result<LasFile, Error> {
+        let point_clouds = self.point_clouds.lock().map_err(|_| {
+            Error::new(ErrorKind::Other, "The in-memory point clouds are unavailable.")
+        })?;
+        point_clouds.get(file_name.trim()).cloned().ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("The in-memory point cloud {} does not exist.", file_name.trim()),
+            )
+        })
+    }
+
+    fn write(&self, file_name: &str, lidar: &LasFile) -> Result<(), Error> {
+        let mut point_clouds = self.point_clouds.lock().map_err(|_| {
+            Error::new(ErrorKind::Other, "The in-memory point clouds are unavailable.")
+        })?;
+        point_clouds.insert(file_name.trim().to_string(), lidar.clone());
+        Ok(())
+    }
+
+    fn exists(&self, file_name: &str) -> bool {
+        self.point_clouds
+            .lock()
+            .map(|p| p.contains_key(file_name.trim()))
+            .unwrap_or(false)
+    }
+}
+
+static PROVIDERS: Mutex<Option<HashMap<String, Arc<dyn LidarProvider>>>> = Mutex::new(None);
+
+/// Registers `provider` for the point clouds named with the URL scheme `scheme` (e.g. `app` for
+/// `app://tile.las`), replacing any provider already registered for it. Schemes are not case
+/// sensitive, and a registered provider takes precedence over the built-in reading of URLs.
+pub fn register_lidar_provider(scheme: &str, provider: Arc<dyn LidarProvider>) {
+    if let Ok(mut providers) = PROVIDERS.lock() {
+        providers
+            .get_or_insert_with(HashMap::new)
+            .insert(scheme.trim().to_lowercase(), provider);
+    }
+}
+
+/// Removes the LiDAR provider registered for `scheme`, if any.
+pub fn unregister_lidar_provider(scheme: &str) {
+    if let Ok(mut providers) = PROVIDERS.lock() {
+        if let Some(p) = providers.as_mut() {
+            p.remove(&scheme.trim().to_lowercase());
+        }
+    }
+}
+
+/// Returns the provider of the point cloud named `file_name`, or `None` if it is a file.
+pub(crate) fn lidar_provider(file_name: &str) -> Option<Arc<dyn LidarProvider>> {
+    if !is_url(file_name) {
+        return None;
+    }
+    let file_name = file_name.trim();
+    let scheme = file_name[..file_name.find("://")?].to_lowercase();
+    let providers = PROVIDERS.lock().ok()?;
+    providers.as_ref()?.get(&scheme).cloned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{LasHeader, LidarPointRecord, PointData};
+
+    #[test]
+    fn test_lidar_provider() {
+        let provider = Arc::new(MemoryLidarProvider::default());
+        register_lidar_provider("lidtest", provider.clone());
+        let mut output = LasFile::new("LidTest://tile.las", "w").unwrap();
+        let mut header: LasHeader = Default::default();
+        header.x_scale_factor = 0.01;
+        header.y_scale_factor = 0.01;
+        header.z_scale_factor = 0.01;
+        output.add_header(header);
+        for i in 0..3 {
+            let mut point_data: PointData = Default::default();
+            point_data.x = i * 100;
+            point_data.y = i * 200;
+            point_data.z = 1000;
+            point_data.set_return_number(1);
+            point_data.set_number_of_returns(1);
+            output.add_point_record(LidarPointRecord::PointRecord0 { point_data });
+        }
+        output.write().unwrap();
+        assert!(provider.exists("LidTest://tile.las"));
+
+        let input = LasFile::new("LidTest://tile.las", "r").unwrap();
+        assert_eq!(input.header.number_of_points, 3);
+        assert_eq!(input.get_point_info(2).y, 400);
+        assert!(input.clone().write().is_err()); // read-only
+
+        unregister_lidar_provider("LIDTEST");
+        assert!(LasFile::new("LidTest://tile.las", "r").is_err());
+    }
+}
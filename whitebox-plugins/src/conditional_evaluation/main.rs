@@ -15,8 +15,8 @@ use std::time::Instant;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
-use num_cpus;
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::errors::ErrorCause;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 use whitebox_raster::*;
 // use v_eval::{Value, Eval};
 use fasteval;
@@ -115,9 +115,10 @@ fn main() {
     let args: Vec<String> = env::args().collect();
 
     if args[1].trim() == "run" {
-        match run(&args) {
-            Ok(_) => {}
-            Err(e) => panic!("{:?}", e),
+        if let Err(e) = run(&args) {
+            // exit with the code of the cause, which the parent process reports
+            eprintln!("Error: {}", e);
+            std::process::exit(ErrorCause::of(&e).exit_code());
         }
     }
 
@@ -192,7 +193,6 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     if !working_directory.is_empty() && !working_directory.ends_with(&sep) {
         working_directory += &sep;
     }
-    let max_procs = configurations.max_procs;
 
     // read the arguments
     let mut input_file = String::new();
@@ -277,12 +277,8 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let start = Instant::now();
 
-    if !input_file.contains(&sep) && !input_file.contains("/") {
-        input_file = format!("{}{}", working_directory, input_file);
-    }
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    input_file = resolve_input_path(&input_file, &working_directory)?;
+    output_file = resolve_path(&output_file, &working_directory);
 
     // Read in the input file
     let input = Arc::new(Raster::new(&input_file, "r")?);
@@ -306,8 +302,8 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         true_value.trim().to_lowercase() == "null" {
             true_constant = nodata;
             is_true_a_constant = true;
-        } else if !true_value.contains(&sep) && !true_value.contains("/") {
-            true_value = format!("{}{}", working_directory, true_value);
+        } else {
+            true_value = resolve_input_path(&true_value, &working_directory)?;
         }
     }
 
@@ -325,8 +321,8 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         false_value.trim().to_lowercase() == "null" {
             false_constant = nodata;
             is_false_a_constant = true;
-        } else if !false_value.contains(&sep) && !false_value.contains("/") {
-            false_value = format!("{}{}", working_directory, false_value);
+        } else {
+            false_value = resolve_input_path(&false_value, &working_directory)?;
         }
     }
 
@@ -369,7 +365,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     let true_raster = if !is_true_a_constant {
         let r = Raster::new(&true_value, "r")?;
         if r.configs.rows as isize != rows || r.configs.columns as isize != columns {
-            panic!("Error: All of the input rasters must share the same rows and columns.");
+            return Err(ErrorCause::GridMismatch.error("All of the input rasters must share the same rows and columns."));
         }
         output.configs.data_type = r.configs.data_type;
         Some(r)
@@ -380,7 +376,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     let false_raster = if !is_false_a_constant {
         let r = Raster::new(&false_value, "r")?;
         if r.configs.rows as isize != rows || r.configs.columns as isize != columns {
-            panic!("Error: All of the input rasters must share the same rows and columns.");
+            return Err(ErrorCause::GridMismatch.error("All of the input rasters must share the same rows and columns."));
         }
         output.configs.data_type = r.configs.data_type;
         Some(r)
@@ -391,10 +387,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     let true_raster = Arc::new(true_raster);
     let false_raster = Arc::new(false_raster);
 
-    let mut num_procs = num_cpus::get() as isize;
-    if max_procs > 0 && max_procs < num_procs {
-        num_procs = max_procs;
-    }
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
 
     // calculate the number of inflowing cells
     let (tx, rx) = mpsc::channel();
@@ -449,10 +442,10 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                     // e = e.insert("column", &format!("{}", col)).unwrap();
                     // e = e.insert("columnx", &format!("{}", input.get_x_from_column(col))).unwrap();
                     value = input.get_value(row, col);
-                    if value != nodata || statement_contains_nodata {
+                    if !is_nodata(value, nodata) || statement_contains_nodata {
                         if let Some(ref tr) = *true_raster {
                             true_val = tr.get_value(row, col);
-                            if true_val == tr.configs.nodata {
+                            if is_nodata(true_val, tr.configs.nodata) {
                                 true_val = nodata;
                             }
                         } else {
@@ -461,7 +454,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
                         if let Some(ref fr) = *false_raster {
                             false_val = fr.get_value(row, col);
-                            if false_val == fr.configs.nodata {
+                            if is_nodata(false_val, fr.configs.nodata) {
                                 false_val = nodata;
                             }
                         } else {
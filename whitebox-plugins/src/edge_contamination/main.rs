@@ -13,7 +13,7 @@ use std::path;
 use std::str;
 use std::time::Instant;
 use whitebox_common::structures::Array2D;
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 use whitebox_raster::*;
 
 /// This tool identifs grid cells in a DEM for which the upslope area extends beyond the raster data extent, so-called
@@ -201,12 +201,8 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let start = Instant::now();
 
-    if !dem_file.contains(&sep) && !dem_file.contains("/") {
-        dem_file = format!("{}{}", working_directory, dem_file);
-    }
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    dem_file = resolve_input_path(&dem_file, &working_directory)?;
+    output_file = resolve_path(&output_file, &working_directory);
 
     /////////////////////////////////////////////////////
     // Read in the DEM and create a D8 pointer from it //
@@ -254,14 +250,14 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     let mut edge_stack = Vec::with_capacity(num_cells as usize);
     let mut visited: Array2D<u8> = Array2D::new(rows, columns, 1, 0)?;
     for row in 0..rows {
-        if dem.get_value(row, 0) != nodata {
+        if !is_nodata(dem.get_value(row, 0), nodata) {
             edge_stack.push((row, 0));
         } else {
             stack.push((row, 0));
         }
         visited.set_value(row, 0, 2);
 
-        if dem.get_value(row, columns - 1) != nodata {
+        if !is_nodata(dem.get_value(row, columns - 1), nodata) {
             edge_stack.push((row, columns - 1));
         } else {
             stack.push((row, columns - 1));
@@ -270,14 +266,14 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     }
 
     for col in 0..columns {
-        if dem.get_value(0, col) != nodata {
+        if !is_nodata(dem.get_value(0, col), nodata) {
             edge_stack.push((0, col));
         } else {
             stack.push((0, col));
         }
         visited.set_value(0, col, 2);
 
-        if dem.get_value(rows - 1, col) != nodata {
+        if !is_nodata(dem.get_value(rows - 1, col), nodata) {
             edge_stack.push((rows - 1, col));
         } else {
             stack.push((rows - 1, col));
@@ -301,7 +297,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
             col_n = col + dx[i];
             if visited.get_value(row_n, col_n) == 1 {
                 zn = dem.get_value(row_n, col_n);
-                if zn != nodata {
+                if !is_nodata(zn, nodata) {
                     edge_stack.push((row_n, col_n));
                     visited.set_value(row_n, col_n, 2);
                 } else {
@@ -365,7 +361,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
             if dinf {
                 e1 = dem.get_value(row + e1_row[i], col + e1_col[i]);
                 e2 = dem.get_value(row + e2_row[i], col + e2_col[i]);
-                if e1 != nodata && e2 != nodata {
+                if !is_nodata(e1, nodata) && !is_nodata(e2, nodata) {
                     e1 *= z_factor;
                     e2 *= z_factor;
                     if z > e1 && z > e2 {
@@ -426,7 +422,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 row_n = row + dy[i];
                 col_n = col + dx[i];
                 zn = dem.get_value(row_n, col_n);
-                if zn < z && zn != nodata {
+                if zn < z && !is_nodata(zn, nodata) {
                     if d8 {
                         zn *= z_factor;
                         slope = (z - zn) / grid_lengths[i];
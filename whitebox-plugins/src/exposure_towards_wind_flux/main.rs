@@ -17,10 +17,9 @@ use std::time::Instant;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
-use whitebox_common::utils::{ get_formatted_elapsed_time, wrapped_print };
+use whitebox_common::utils::{get_formatted_elapsed_time, wrapped_print, resolve_input_path, resolve_path};
 use whitebox_common::structures::Array2D;
 use whitebox_raster::*;
-use num_cpus;
 
 /// This tool creates a new raster in which each grid cell is assigned the exposure of the land-surface to 
 /// a hypothetical wind flux. It can be conceptualized as the angle between a plane orthogonal to the wind 
@@ -143,7 +142,6 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     if !working_directory.is_empty() && !working_directory.ends_with(&sep) {
         working_directory += &sep;
     }
-    let max_procs = configurations.max_procs as isize;
 
     // read the arguments
     let mut input_file: String = String::new();
@@ -234,12 +232,8 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let start = Instant::now();
 
-    if !input_file.contains(&sep) && !input_file.contains("/") {
-        input_file = format!("{}{}", working_directory, input_file);
-    }
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    input_file = resolve_input_path(&input_file, &working_directory)?;
+    output_file = resolve_path(&output_file, &working_directory);
 
     if azimuth < 0.0 {
         if configurations.verbose_mode {
@@ -305,10 +299,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     ////////////////////////////////////
     // Calculate the slope and aspect //
     ////////////////////////////////////
-    let mut num_procs = num_cpus::get() as isize;
-    if max_procs > 0 && max_procs < num_procs {
-        num_procs = max_procs;
-    }
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
     let (tx, rx) = mpsc::channel();
     for tid in 0..num_procs {
         let input = input.clone();
@@ -325,10 +316,10 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 let mut slope_data = vec![nodata_f32; columns as usize];
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata_f32 {
+                    if !is_nodata(z as f64, nodata_f32 as f64) {
                         for c in 0..8 {
                             n[c] = input.get_value(row + dy[c], col + dx[c]);
-                            if n[c] != nodata_f32 {
+                            if !is_nodata(n[c] as f64, nodata_f32 as f64) {
                                 n[c] = n[c] * z_factor_array[row as usize];
                             } else {
                                 n[c] = z * z_factor_array[row as usize];
@@ -465,7 +456,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 let mut data: Vec<f32> = vec![nodata_f32; columns as usize];
                 for col in 0..columns {
                     current_elev = input.get_value(row, col);
-                    if current_elev != nodata_f32 {
+                    if !is_nodata(current_elev as f64, nodata_f32 as f64) {
                         // Run down the offsets of the ray
                         current_max_slope = a_small_value;
                         current_max_elev = a_small_value;
@@ -480,11 +471,11 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                             z1 = input.get_value(y1, x1);
                             z2 = input.get_value(y2, x2);
 
-                            if z1 == nodata_f32 && z2 == nodata_f32 {
+                            if is_nodata(z1 as f64, nodata_f32 as f64) && is_nodata(z2 as f64, nodata_f32 as f64) {
                                 break; // We're likely off the grid.
-                            } else if z1 == nodata_f32 {
+                            } else if is_nodata(z1 as f64, nodata_f32 as f64) {
                                 z1 = z2;
-                            } else if z2 == nodata_f32 {
+                            } else if is_nodata(z2 as f64, nodata_f32 as f64) {
                                 z2 = z2;
                             }
 
@@ -557,7 +548,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                     if relative_aspect > PI {
                         relative_aspect = 2f32 * PI - relative_aspect;
                     }
-                    if aspect_val != nodata_f32 {
+                    if !is_nodata(aspect_val as f64, nodata_f32 as f64) {
                         slope_val = slope.get_value(row, col);
                         ha_val = horizon_angle.get_value(row, col).max(0f32);
                         // cosγW =sinφ·cosβ+cosφ·sinβ·cosαr
@@ -23,7 +23,7 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 use std::time::Instant;
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 
 /// This tool uses the fast Gaussian approximation algorithm to produce scaled land-surface parameter (LSP)
 /// measurements from an input DEM (`--dem`). The algorithm iterates over scales
@@ -266,25 +266,13 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         }
     };
 
-    let sep: String = path::MAIN_SEPARATOR.to_string();
-
-    if !input_file.contains(&sep) && !input_file.contains("/") {
-        input_file = format!("{}{}", working_directory, input_file);
-    }
+    input_file = resolve_input_path(&input_file, &working_directory)?;
     if points_file.len() > 0 {
-        if !points_file.contains(&sep) && !points_file.contains("/") {
-            points_file = format!("{}{}", working_directory, points_file);
-        }
-    }
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
-    if !output_scale_file.contains(&sep) && !output_scale_file.contains("/") {
-        output_scale_file = format!("{}{}", working_directory, output_scale_file);
-    }
-    if !output_zscore_file.contains(&sep) && !output_zscore_file.contains("/") {
-        output_zscore_file = format!("{}{}", working_directory, output_zscore_file);
+        points_file = resolve_input_path(&points_file, &working_directory)?;
     }
+    output_file = resolve_path(&output_file, &working_directory);
+    output_scale_file = resolve_path(&output_scale_file, &working_directory);
+    output_zscore_file = resolve_path(&output_zscore_file, &working_directory);
 
     // LSP signature output file
     let p = path::Path::new(&output_file);
@@ -312,7 +300,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     let nodata = configs.nodata;
     let nodata32 = nodata as f32;
 
-    let num_procs = num_cpus::get() as isize;
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
 
     if sigma_i < 0f64 {
         eprintln!("Warning: Sigma must be >= 0. Value set to 0.0.");
@@ -394,7 +382,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         sum_n = 0;
         for col in 0..columns {
             val = input_raster.get_value(row, col);
-            if val == nodata {
+            if is_nodata(val, nodata) {
                 val = 0f64;
             } else {
                 sum_n += 1;
@@ -534,13 +522,13 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut smoothed_data = vec![nodata; columns as usize];
                         for col in 0..columns {
-                            if inp.get_value(row, col) as f64 != nodata {
+                            if !is_nodata(inp.get_value(row, col) as f64, nodata) {
                                 sum = 0f64;
                                 z_final = 0f64;
                                 for a in 0..num_pixels_in_filter {
                                     w = weights[a as usize];
                                     zn = inp.get_value(row + dy[a as usize], col + dx[a as usize]) as f64;
-                                    if zn != nodata {
+                                    if !is_nodata(zn, nodata) {
                                         sum += w;
                                         z_final += w * zn;
                                     }
@@ -601,7 +589,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                         sum = 0f64;
                         for col in 0..columns {
                             val = smoothed_elev.get_value(row, col);
-                            val = val * (val != nodata) as usize as f64; // val if val!=0 else 0
+                            val = val * (!is_nodata(val, nodata)) as usize as f64; // val if val!=0 else 0
                             sum += val;
                             if row > 0 {
                                 i_prev = integral_mod.get_value(row - 1, col);
@@ -624,7 +612,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                         y2 = rows - 1;
                     }
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata32 {
+                        if !is_nodata(input.get_value(row, col) as f64, nodata32 as f64) {
                             x1 = col - midpoint - 1;
                             x1 = x1 * (x1 >= 0) as isize;
                             x2 = col + midpoint;
@@ -693,16 +681,16 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                         is_valid = true;
                         val = nodata;
                         n[8] = inp.get_value(row, col);
-                        if n[8] != nodata {
+                        if !is_nodata(n[8], nodata) {
                             n[8] *= z_factor_array[row as usize];
                             for c in 0..8 {
-                                if sigma >= 3f64 && inp.get_value(row + (d_y[c] * buffer), col + (d_x[c] * buffer)) == nodata {
+                                if sigma >= 3f64 && is_nodata(inp.get_value(row + (d_y[c] * buffer), col + (d_x[c] * buffer)), nodata) {
                                     // inp from fast gaussian and has nodata within midpoint
                                     is_valid = false;
                                     break
                                 }
                                 n[c] = inp.get_value(row + d_y[c], col + d_x[c]);
-                                if n[c] != nodata {
+                                if !is_nodata(n[c], nodata) {
                                     n[c] *= z_factor_array[row as usize];
                                 } else if lsp_func != fn_dme && lsp_func != fn_ruggedness {
                                     n[c] = n[8];
@@ -712,7 +700,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                                 val = lsp_func(n, res, nodata);
                             } // else val stays nodata
 
-                            if val != nodata {
+                            if !is_nodata(val, nodata) {
                                 n_part += 1isize;
                                 s_part += val;
                                 sq_part += val * val;
@@ -728,7 +716,6 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         }
 
 
-
         let mut num = 0;
         let mut sum = 0f64;
         let mut sumsqr = 0f64;
@@ -760,7 +747,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 for row in buffer..rows-buffer {
                     for col in buffer..columns-buffer {
                         lsp_val = lsp_data.get_value(row, col);
-                        if lsp_val != nodata {
+                        if !is_nodata(lsp_val, nodata) {
                             if lsp_func == fn_aspect {
                                 zlsp = degrees_diff(lsp_val, mean) / stddev;
                             } else {
@@ -768,7 +755,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                             }
                             tmp_zsc.set_value(row, col, zlsp as f32);
                             zmax = output_zsc.get_value(row, col) as f64;
-                            if zmax != nodata {
+                            if !is_nodata(zmax, nodata) {
                                 if zlsp.abs() > zmax.abs() {
                                     output_lsp.set_value(row, col, lsp_val as f32);
                                     output_scl.set_value(row, col, sigma_actual as f32);
@@ -794,14 +781,14 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 for row in buffer..rows-buffer {
                     for col in buffer..columns-buffer {
                         lsp_val = lsp_data.get_value(row, col);
-                        if lsp_val != nodata {
+                        if !is_nodata(lsp_val, nodata) {
                             if lsp_func == fn_aspect {
                                 zlsp = degrees_diff(lsp_val, mean) / stddev;
                             } else {
                                 zlsp = (lsp_val - mean) / stddev;
                             }
                             zmax = output_zsc.get_value(row, col) as f64;
-                            if zmax != nodata {
+                            if !is_nodata(zmax, nodata) {
                                 if zlsp.abs() > zmax.abs() {
                                     output_lsp.set_value(row, col, lsp_val as f32);
                                     output_scl.set_value(row, col, sigma_actual as f32);
@@ -1005,7 +992,7 @@ fn fn_anisotropy(n: [f64; 9], _r:f64, nodata:f64) -> f64 {
     let (full_delta, ns_delta, ew_delta, nesw_delta, nwse_delta): (f64, f64, f64, f64, f64);
     let (mut nn, mut s) = (0usize, 0f64);
     for i in 0..n.len() {
-        if n[i] != nodata {
+        if !is_nodata(n[i], nodata) {
             nn += 1;
             s += n[i];
         }
@@ -1013,20 +1000,20 @@ fn fn_anisotropy(n: [f64; 9], _r:f64, nodata:f64) -> f64 {
     if nn > 0 {
         full_delta = n[8] - (s / (nn as f64));
         // N-S pane
-        s = n[8] + (n[7] * (n[7]!=nodata) as usize as f64) + (n[3] * (n[3]!=nodata) as usize as f64);
-        nn = 1 + (1 * (n[7]!=nodata) as usize) + (1 * (n[3]!=nodata) as usize);
+        s = n[8] + (n[7] * (!is_nodata(n[7], nodata)) as usize as f64) + (n[3] * (!is_nodata(n[3], nodata)) as usize as f64);
+        nn = 1 + (1 * (!is_nodata(n[7], nodata)) as usize) + (1 * (!is_nodata(n[3], nodata)) as usize);
         ns_delta = n[8] - (s / (nn as f64)) - full_delta;
         // E-W pane
-        s = n[8] + (n[5] * (n[5]!=nodata) as usize as f64) + (n[1] * (n[1]!=nodata) as usize as f64);
-        nn = 1 + (1 * (n[5]!=nodata) as usize) + (1 * (n[1]!=nodata) as usize);
+        s = n[8] + (n[5] * (!is_nodata(n[5], nodata)) as usize as f64) + (n[1] * (!is_nodata(n[1], nodata)) as usize as f64);
+        nn = 1 + (1 * (!is_nodata(n[5], nodata)) as usize) + (1 * (!is_nodata(n[1], nodata)) as usize);
         ew_delta = n[8] - (s / (nn as f64)) - full_delta;
         // NE-SW pane
-        s = n[8] + (n[0] * (n[0]!=nodata) as usize as f64) + (n[4] * (n[4]!=nodata) as usize as f64);
-        nn = 1 + (1 * (n[0]!=nodata) as usize) + (1 * (n[4]!=nodata) as usize);
+        s = n[8] + (n[0] * (!is_nodata(n[0], nodata)) as usize as f64) + (n[4] * (!is_nodata(n[4], nodata)) as usize as f64);
+        nn = 1 + (1 * (!is_nodata(n[0], nodata)) as usize) + (1 * (!is_nodata(n[4], nodata)) as usize);
         nesw_delta = n[8] - (s / (nn as f64)) - full_delta;
         // NW-SE pane
-        s = n[8] + (n[6] * (n[6]!=nodata) as usize as f64) + (n[2] * (n[2]!=nodata) as usize as f64);
-        nn = 1 + (1 * (n[6]!=nodata) as usize) + (1 * (n[2]!=nodata) as usize);
+        s = n[8] + (n[6] * (!is_nodata(n[6], nodata)) as usize as f64) + (n[2] * (!is_nodata(n[2], nodata)) as usize as f64);
+        nn = 1 + (1 * (!is_nodata(n[6], nodata)) as usize) + (1 * (!is_nodata(n[2], nodata)) as usize);
         nwse_delta = n[8] - (s / (nn as f64)) - full_delta;
 
         //final value
@@ -1056,7 +1043,7 @@ fn fn_aspect(n: [f64; 9], r: f64, nodata:f64) -> f64 {
 fn fn_dme(n: [f64; 9], _r: f64, nodata: f64) -> f64 {
     let (mut nn, mut s) = (0usize, 0f64);
     for i in 0..n.len() {
-        if n[i] != nodata {
+        if !is_nodata(n[i], nodata) {
             nn += 1;
             s += n[i];
         }
@@ -1066,7 +1053,7 @@ fn fn_dme(n: [f64; 9], _r: f64, nodata: f64) -> f64 {
 
 fn fn_eastness(n: [f64; 9], r: f64, nodata: f64) -> f64 {
     let a = fn_aspect(n, r, nodata);
-    if a != nodata {
+    if !is_nodata(a, nodata) {
         a.to_radians().sin()
     } else {
         nodata
@@ -1126,7 +1113,7 @@ fn fn_mean_curvature(n: [f64;9], r: f64, _nodata: f64) -> f64 {
 
 fn fn_northness(n: [f64; 9], r: f64, nodata:f64) -> f64 {
     let a = fn_aspect(n, r, nodata);
-    if a != nodata {
+    if !is_nodata(a, nodata) {
         a.to_radians().cos()
     } else {
         nodata
@@ -1178,7 +1165,7 @@ fn fn_ruggedness(n: [f64; 9], _r: f64, nodata: f64) -> f64 {
     let mut nn = 0usize;
     let mut ss = 0f64;
     for i in 0..8 {
-        if n[i] != nodata {
+        if !is_nodata(n[i], nodata) {
             ss += (n[8] - n[i]) * (n[8] - n[i]);
             nn += 1;
         }
@@ -18,8 +18,7 @@ use std::time::Instant;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
-use num_cpus;
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 use whitebox_raster::*;
 use whitebox_vector::{
     FieldData,
@@ -134,7 +133,6 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     if !working_directory.is_empty() && !working_directory.ends_with(&sep) {
         working_directory += &sep;
     }
-    let max_procs = configurations.max_procs;
 
 
     // read the arguments
@@ -234,13 +232,9 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let start = Instant::now();
 
-    if !input_file.contains(&sep) && !input_file.contains("/") {
-        input_file = format!("{}{}", working_directory, input_file);
-    }
+    input_file = resolve_input_path(&input_file, &working_directory)?;
 
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    output_file = resolve_path(&output_file, &working_directory);
 
     if bandwidth <= 0f64 {
         return Err(Error::new(
@@ -348,9 +342,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     }
 
     let mut output = if !base_file.trim().is_empty() || cell_size == 0f64 {
-        if !base_file.contains(&sep) && !base_file.contains("/") {
-            base_file = format!("{}{}", working_directory, base_file);
-        }
+        base_file = resolve_input_path(&base_file, &working_directory)?;
         let base = Raster::new(&base_file, "r")?;
         Raster::initialize_using_file(&output_file, &base)
     } else {
@@ -376,8 +368,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         configs.resolution_y = cell_size;
         configs.nodata = -32768.0;
         configs.photometric_interp = PhotometricInterpretation::Continuous;
-        // configs.epsg_code = vector_points.projection.clone();
-        configs.projection = vector_points.projection.clone();
+        configs.set_crs(&whitebox_common::spatial_ref_system::CoordinateReferenceSystem::from_wkt(&vector_points.projection));
 
         Raster::initialize_using_config(&output_file, &configs)
     };
@@ -426,10 +417,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         process::exit(1);
     }));
 
-    let mut num_procs = num_cpus::get() as isize;
-    if max_procs > 0 && max_procs < num_procs {
-        num_procs = max_procs;
-    }
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
     let (tx, rx) = mpsc::channel();
     for tid in 0..num_procs {
         let kdtree = kdtree.clone();
@@ -12,7 +12,7 @@ use std::path;
 use std::str;
 use std::time::Instant;
 use whitebox_lidar::*;
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 
 /// This tool can be used to shift the x,y,z coordinates of points within a LiDAR file. The user must specify 
 /// the name of the input file (`--input`) and the output file (`--output`). Additionally, the user must specify
@@ -201,12 +201,8 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         ));
     }
 
-    if !input_file.contains(&sep) && !input_file.contains("/") {
-        input_file = format!("{}{}", working_directory, input_file);
-    }
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    input_file = resolve_input_path(&input_file, &working_directory)?;
+    output_file = resolve_path(&output_file, &working_directory);
 
     let mut progress: usize;
     let mut old_progress: usize = 1;
@@ -8,7 +8,6 @@ License: MIT
 
 use whitebox_raster::*;
 use nalgebra::{Matrix5, RowVector5, Vector5};
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
@@ -17,7 +16,7 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 use std::time::Instant;
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 
 /// This tool is an implementation of the constrained quadratic regression algorithm
 /// using a flexible window size described in Wood (1996). A quadratic surface is fit
@@ -190,17 +189,11 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         println!("{}", "*".repeat(welcome_len));
     }
 
-    let sep: String = path::MAIN_SEPARATOR.to_string();
-
     let mut progress: usize;
     let mut old_progress: usize = 1;
 
-    if !input_file.contains(&sep) && !input_file.contains("/") {
-        input_file = format!("{}{}", working_directory, input_file);
-    }
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    input_file = resolve_input_path(&input_file, &working_directory)?;
+    output_file = resolve_path(&output_file, &working_directory);
 
     if configurations.verbose_mode {
         println!("Reading data...")
@@ -244,7 +237,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         }
     }
 
-    let num_procs = num_cpus::get() as isize;
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
     let (tx, rx) = mpsc::channel();
     for tid in 0..num_procs {
         let input = input.clone();
@@ -272,7 +265,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
                     z = input[(row, col)];
 
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
 
                         let (mut zx2, mut zy2, mut zxy, mut zx, mut zy, mut _zw) = (0f64,0f64,0f64,0f64,0f64,0f64);
                         let (mut x2, mut x2y2, mut x4) = (0f64, 0f64, 0f64);
@@ -287,7 +280,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
                         for c in 0..num_cells {
                             zi = input[((row + dy[c] as isize), (col + dx[c] as isize))];
-                            if zi != nodata {
+                            if !is_nodata(zi, nodata) {
                                 xs.push(dx[c] as f64 * resolution);
                                 ys.push(dy[c] as f64 * resolution);
                                 zs.push(zi - z); // elevation relative to center
@@ -14,9 +14,8 @@ use std::time::Instant;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
-use num_cpus;
 use whitebox_common::structures::{Array2D};
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 use whitebox_raster::*;
 
 /// This tool is used to can be used calculate the maximum upslope value, based on the values within an
@@ -163,15 +162,9 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let start = Instant::now();
 
-    if !dem_file.contains(&sep) && !dem_file.contains("/") {
-        dem_file = format!("{}{}", working_directory, dem_file);
-    }
-    if !values_file.contains(&sep) && !values_file.contains("/") {
-        values_file = format!("{}{}", working_directory, values_file);
-    }
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    dem_file = resolve_input_path(&dem_file, &working_directory)?;
+    values_file = resolve_input_path(&values_file, &working_directory)?;
+    output_file = resolve_path(&output_file, &working_directory);
 
     // println!("{dem_file}");
     // println!("{values_file}");
@@ -189,12 +182,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
     let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -2, -2)?;
     let mut interior_pit_found = false;
-    let mut num_procs = num_cpus::get() as isize;
-    let configs = whitebox_common::configs::get_configs()?;
-    let max_procs = configs.max_procs;
-    if max_procs > 0 && max_procs < num_procs {
-        num_procs = max_procs;
-    }
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
 
     let (tx, rx) = mpsc::channel();
     for tid in 0..num_procs {
@@ -223,13 +211,13 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 let mut data: Vec<i8> = vec![-2i8; columns as usize];
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         dir = 0i8;
                         max_slope = f64::MIN;
                         neighbouring_nodata = false;
                         for i in 0..8 {
                             z_n = input[(row + dy[i], col + dx[i])];
-                            if z_n != nodata {
+                            if !is_nodata(z_n, nodata) {
                                 slope = (z - z_n) / grid_lengths[i];
                                 if slope > max_slope && slope > 0f64 {
                                     max_slope = slope;
@@ -14,9 +14,8 @@ use std::time::Instant;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
-use num_cpus;
 use whitebox_common::structures::{Array2D};
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 use whitebox_raster::*;
 
 /// This tool is used to generate a flow accumulation grid (i.e. contributing area) using the Quinn et al. (1995) 
@@ -262,12 +261,8 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let start = Instant::now();
 
-    if !dem_file.contains(&sep) && !dem_file.contains("/") {
-        dem_file = format!("{}{}", working_directory, dem_file);
-    }
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    dem_file = resolve_input_path(&dem_file, &working_directory)?;
+    output_file = resolve_path(&output_file, &working_directory);
 
     if convergence_threshold <= 0f64 {
         convergence_threshold = f64::MAX;
@@ -297,11 +292,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         z_factor = 1.0;
     }
 
-    let mut num_procs = num_cpus::get() as isize;
-    let max_procs = configurations.max_procs;
-    if max_procs > 0 && max_procs < num_procs {
-        num_procs = max_procs;
-    }
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
 
     // calculate the number of inflowing cells
     let (tx, rx) = mpsc::channel();
@@ -319,11 +310,11 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 let mut data: Vec<i8> = vec![-1i8; columns as usize];
                 for col in 0..columns {
                     z = dem.get_value(row, col);
-                    if z != dem_nodata {
+                    if !is_nodata(z, dem_nodata) {
                         count = 0i8;
                         for i in 0..8 {
                             zn = dem.get_value(row + dy[i], col + dx[i]);
-                            if zn > z && zn != dem_nodata {
+                            if zn > z && !is_nodata(zn, dem_nodata) {
                                 count += 1;
                             }
                         }
@@ -418,7 +409,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 row_n = row + dy[i];
                 col_n = col + dx[i];
                 zn = dem.get_value(row_n, col_n);
-                if zn < z && zn != dem_nodata {
+                if zn < z && !is_nodata(zn, dem_nodata) {
                     zn *= z_factor;
                     slope = (z - zn) / grid_lengths[i];
                     f = (fa / convergence_threshold + 1f64).powf(exponent);
@@ -439,7 +430,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
             max_slope = f64::MIN;
             for i in 0..8 {
                 zn = dem.get_value(row + dy[i], col + dx[i]);
-                if zn != dem_nodata {
+                if !is_nodata(zn, dem_nodata) {
                     slope = (z - zn) / grid_lengths[i];
                     if slope > 0f64 {
                         downslope[i] = true;
@@ -493,14 +484,14 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         for row in 0..rows {
             for col in 0..columns {
                 z = dem.get_value(row, col);
-                if z == dem_nodata {
+                if is_nodata(z, dem_nodata) {
                     output.set_value(row, col, dem_nodata);
                 } else {
                     if out_type == "sca" {
                         contour_length = 0.0;
                         for i in 0..8 {
                             zn = dem.get_value(row + dy[i], col + dx[i]);
-                            if zn < z && zn != dem_nodata {
+                            if zn < z && !is_nodata(zn, dem_nodata) {
                                 contour_length += contour_lengths[i];
                             }
                         }
@@ -526,14 +517,14 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         for row in 0..rows {
             for col in 0..columns {
                 z = dem.get_value(row, col);
-                if z == dem_nodata {
+                if is_nodata(z, dem_nodata) {
                     output.set_value(row, col, dem_nodata);
                 } else {
                     if out_type == "sca" {
                         contour_length = 0.0;
                         for i in 0..8 {
                             zn = dem.get_value(row + dy[i], col + dx[i]);
-                            if zn < z && zn != dem_nodata {
+                            if zn < z && !is_nodata(zn, dem_nodata) {
                                 contour_length += contour_lengths[i];
                             }
                         }
@@ -0,0 +1,509 @@
+/*
+Authors: Prof. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+NOTES: GPU evaluation of the RasterCalculator statement, used when the tool is run with the
+--compute=gpu flag. The statement is parsed and compiled by fasteval, as on the CPU, and the
+compiled instructions are translated into a WGSL compute shader that evaluates the statement for
+every grid cell. The GPU code is only compiled with the 'gpu' cargo feature. `evaluate` returns
+None when the statement cannot be evaluated on a GPU, e.g. because it calls a function that has no
+GPU equivalent or no GPU is available, in which case the tool carries on with its CPU
+implementation. Calculations are performed in single precision, and so results may differ slightly
+from those calculated on the CPU, particularly for statements using the cell coordinates.
+*/
+
+#![cfg_attr(not(feature = "gpu"), allow(dead_code))]
+
+use fasteval::compiler::{Instruction, IC};
+use fasteval::{Compiler, Parser, Slab};
+use whitebox_raster::Raster;
+
+/// Evaluates `statement` for every cell of the `input` rasters on the GPU, returning the output
+/// rows. Cells for which any input is NoData are assigned `out_nodata`, unless
+/// `statement_contains_nodata`. Returns `None`, after printing the reason when `verbose`, if the
+/// statement could not be evaluated on a GPU.
+#[cfg(not(feature = "gpu"))]
+pub fn evaluate(
+    _statement: &str,
+    _input: &[Raster],
+    _statement_contains_nodata: bool,
+    _out_nodata: f64,
+    verbose: bool,
+) -> Option<Vec<Vec<f64>>> {
+    if verbose {
+        println!("Warning: This build does not include GPU support; falling back to the CPU.");
+    }
+    None
+}
+
+#[cfg(feature = "gpu")]
+pub fn evaluate(
+    statement: &str,
+    input: &[Raster],
+    statement_contains_nodata: bool,
+    out_nodata: f64,
+    verbose: bool,
+) -> Option<Vec<Vec<f64>>> {
+    let result = translate(statement, input)
+        .and_then(|expression| device::evaluate(&expression, input, statement_contains_nodata, out_nodata, verbose));
+    match result {
+        Ok(rows) => Some(rows),
+        Err(msg) => {
+            if verbose {
+                println!("Warning: {}; falling back to the CPU.", msg);
+            }
+            None
+        }
+    }
+}
+
+/// Translates `statement`, in which the input rasters are named `value0`, `value1`, etc., into a
+/// WGSL expression for the cell `id.x`, `id.y` of a strip of rows (see the shader in `device`).
+fn translate(statement: &str, input: &[Raster]) -> Result<String, String> {
+    let mut slab = Slab::new();
+    let expr_i = Parser::new()
+        .parse(statement, &mut slab.ps)
+        .map_err(|e| format!("The statement could not be parsed ({:?})", e))?;
+    let instr = expr_i.from(&slab.ps).compile(&slab.ps, &mut slab.cs);
+    Translator { slab: &slab, input }.instr(&instr)
+}
+
+struct Translator<'a> {
+    slab: &'a Slab,
+    input: &'a [Raster],
+}
+
+impl<'a> Translator<'a> {
+    fn ic(&self, ic: &IC) -> Result<String, String> {
+        match ic {
+            IC::I(i) => self.instr(self.slab.cs.get_instr(*i)),
+            IC::C(c) => Ok(literal(*c)),
+        }
+    }
+
+    fn instr(&self, instr: &Instruction) -> Result<String, String> {
+        use fasteval::compiler::Instruction::*;
+        let get = |i| self.instr(self.slab.cs.get_instr(i));
+        Ok(match instr {
+            IConst(c) => literal(*c),
+            INeg(i) => format!("(-{})", get(*i)?),
+            INot(i) => format!("wb_bool({} == 0.0)", get(*i)?),
+            IInv(i) => format!("(1.0 / {})", get(*i)?),
+            IAdd(a, b) => format!("({} + {})", get(*a)?, self.ic(b)?),
+            IMul(a, b) => format!("({} * {})", get(*a)?, self.ic(b)?),
+            IMod { dividend, divisor } => format!("({} % {})", self.ic(dividend)?, self.ic(divisor)?),
+            IExp { base, power } => format!("wb_pow({}, {})", self.ic(base)?, self.ic(power)?),
+            ILT(a, b) => format!("wb_bool({} < {})", self.ic(a)?, self.ic(b)?),
+            ILTE(a, b) => format!("wb_bool({} <= {})", self.ic(a)?, self.ic(b)?),
+            IEQ(a, b) => format!("wb_bool({} == {})", self.ic(a)?, self.ic(b)?),
+            INE(a, b) => format!("wb_bool({} != {})", self.ic(a)?, self.ic(b)?),
+            IGTE(a, b) => format!("wb_bool({} >= {})", self.ic(a)?, self.ic(b)?),
+            IGT(a, b) => format!("wb_bool({} > {})", self.ic(a)?, self.ic(b)?),
+            // as in fasteval, || returns the first non-zero operand and && the first zero operand
+            IOR(a, b) => format!("wb_or({}, {})", get(*a)?, self.ic(b)?),
+            IAND(a, b) => format!("wb_and({}, {})", get(*a)?, self.ic(b)?),
+            IVar(name) => self.var(name)?,
+            IFuncInt(i) => format!("trunc({})", get(*i)?),
+            IFuncCeil(i) => format!("ceil({})", get(*i)?),
+            IFuncFloor(i) => format!("floor({})", get(*i)?),
+            IFuncAbs(i) => format!("abs({})", get(*i)?),
+            IFuncSign(i) => format!("wb_sign({})", get(*i)?),
+            IFuncLog { base, of } => format!("wb_log({}, {})", self.ic(base)?, self.ic(of)?),
+            IFuncRound { modulus, of } => format!("wb_round({}, {})", self.ic(modulus)?, self.ic(of)?),
+            IFuncMin(a, b) => format!("min({}, {})", get(*a)?, self.ic(b)?),
+            IFuncMax(a, b) => format!("max({}, {})", get(*a)?, self.ic(b)?),
+            IFuncSin(i) => format!("sin({})", get(*i)?),
+            IFuncCos(i) => format!("cos({})", get(*i)?),
+            IFuncTan(i) => format!("tan({})", get(*i)?),
+            IFuncASin(i) => format!("asin({})", get(*i)?),
+            IFuncACos(i) => format!("acos({})", get(*i)?),
+            IFuncATan(i) => format!("atan({})", get(*i)?),
+            IFuncSinH(i) => format!("sinh({})", get(*i)?),
+            IFuncCosH(i) => format!("cosh({})", get(*i)?),
+            IFuncTanH(i) => format!("tanh({})", get(*i)?),
+            IFuncASinH(i) => format!("asinh({})", get(*i)?),
+            IFuncACosH(i) => format!("acosh({})", get(*i)?),
+            IFuncATanH(i) => format!("atanh({})", get(*i)?),
+            IFunc { name, .. } => return Err(format!("The function '{}' is not supported on the GPU", name)),
+            _ => return Err("The statement is not supported on the GPU".to_string()),
+        })
+    }
+
+    /// The WGSL expression of one of the variables of the statement.
+    fn var(&self, name: &str) -> Result<String, String> {
+        let configs = &self.input[0].configs;
+        Ok(match name {
+            "row" => "f32(params.start_row + id.y)".to_string(),
+            "column" => "f32(id.x)".to_string(),
+            // the coordinates follow the input values in the input buffer
+            "rowy" => format!("input[{}u * cells + id.y]", self.input.len()),
+            "columnx" => format!("input[{}u * cells + params.rows + id.x]", self.input.len()),
+            "rows" => literal(configs.rows as f64),
+            "columns" => literal(configs.columns as f64),
+            "north" => literal(configs.north),
+            "south" => literal(configs.south),
+            "east" => literal(configs.east),
+            "west" => literal(configs.west),
+            "cellsizex" => literal(configs.resolution_x),
+            "cellsizey" => literal(configs.resolution_y),
+            "cellsize" => literal((configs.resolution_x + configs.resolution_y) / 2.0),
+            _ => match name.strip_prefix("value").and_then(|i| i.parse::<usize>().ok()) {
+                Some(i) if i < self.input.len() => format!("input[{}u * cells + idx]", i),
+                _ => return Err(format!("The variable '{}' is not defined", name)),
+            },
+        })
+    }
+}
+
+/// A single-precision WGSL literal, written as its bit pattern so that infinite and NaN values can
+/// be represented too.
+fn literal(value: f64) -> String {
+    format!("bitcast<f32>({}u)", (value as f32).to_bits())
+}
+
+#[cfg(feature = "gpu")]
+mod device {
+    use std::borrow::Cow;
+    use std::sync::mpsc;
+    use whitebox_raster::{is_nodata, Raster};
+    use wgpu::util::DeviceExt;
+
+    /// The largest input strip transferred to the GPU at once, in bytes.
+    const MAX_STRIP_BYTES: u64 = 64 * 1024 * 1024;
+
+    /// The shader, in which EXPRESSION is replaced by the translated statement. The input buffer
+    /// holds the values of each input raster for the cells of the strip, followed by the y
+    /// coordinates of the strip's rows and the x coordinates of the columns.
+    const SHADER: &str = r#"
+struct Params {
+    rows: u32,
+    columns: u32,
+    start_row: u32,
+    pad0: u32,
+};
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> input: array<f32>;
+@group(0) @binding(2) var<storage, read_write> output: array<f32>;
+
+fn wb_bool(b: bool) -> f32 {
+    return select(0.0, 1.0, b);
+}
+
+fn wb_or(a: f32, b: f32) -> f32 {
+    return select(b, a, a != 0.0);
+}
+
+fn wb_and(a: f32, b: f32) -> f32 {
+    return select(b, a, a == 0.0);
+}
+
+// Rust's powf, which, unlike WGSL's pow, is defined for negative bases with integer powers.
+fn wb_pow(a: f32, b: f32) -> f32 {
+    if (b == 0.0) {
+        return 1.0;
+    }
+    if (a < 0.0 && b == floor(b)) {
+        let p = pow(-a, b);
+        return select(p, -p, abs(b) % 2.0 == 1.0);
+    }
+    return pow(a, b);
+}
+
+// Rust's signum, which is 1 for zero.
+fn wb_sign(x: f32) -> f32 {
+    return select(-1.0, 1.0, x >= 0.0);
+}
+
+fn wb_log(base: f32, x: f32) -> f32 {
+    if (base == 2.0) {
+        return log2(x);
+    }
+    return log(x) / log(base);
+}
+
+// Rounds halfway values away from zero, as Rust's round does.
+fn wb_round(modulus: f32, x: f32) -> f32 {
+    let v = x / modulus;
+    return sign(v) * floor(abs(v) + 0.5) * modulus;
+}
+
+@compute @workgroup_size(16, 16)
+fn evaluate(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.columns || id.y >= params.rows) {
+        return;
+    }
+    let cells = params.rows * params.columns;
+    let idx = id.y * params.columns + id.x;
+    output[idx] = EXPRESSION;
+}
+"#;
+
+    pub(super) fn evaluate(
+        expression: &str,
+        input: &[Raster],
+        statement_contains_nodata: bool,
+        out_nodata: f64,
+        verbose: bool,
+    ) -> Result<Vec<Vec<f64>>, String> {
+        let rows = input[0].configs.rows;
+        let columns = input[0].configs.columns;
+        let num_inputs = input.len();
+        if rows == 0 || columns == 0 {
+            return Err("The input raster is empty".to_string());
+        }
+
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .ok_or("No GPU adapter is available")?;
+        if verbose {
+            println!("Using GPU: {}", adapter.get_info().name);
+        }
+        let limits = adapter.limits();
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                required_features: wgpu::Features::empty(),
+                required_limits: limits.clone(),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        ))
+        .map_err(|e| format!("The GPU device could not be initialized ({})", e))?;
+
+        // Size the strips of rows processed by each dispatch to respect the device limits.
+        let max_values = (MAX_STRIP_BYTES
+            .min(limits.max_storage_buffer_binding_size as u64)
+            .min(limits.max_buffer_size)
+            / 4) as usize;
+        if max_values <= columns {
+            return Err("The input raster has too many columns for the GPU".to_string());
+        }
+        let max_workgroups = limits.max_compute_workgroups_per_dimension as usize;
+        if (columns + 15) / 16 > max_workgroups {
+            return Err("The input raster has too many columns for the GPU".to_string());
+        }
+        let strip_rows = ((max_values - columns) / (num_inputs * columns + 1))
+            .min(max_workgroups * 16)
+            .min(rows);
+        if strip_rows == 0 {
+            return Err("The input rasters have too many columns for the GPU".to_string());
+        }
+
+        // Capture validation and allocation errors, which wgpu would otherwise treat as fatal, so
+        // that the tool can fall back to the CPU instead.
+        device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(Cow::Owned(SHADER.replace("EXPRESSION", expression))),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: "evaluate",
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        let layout = pipeline.get_bind_group_layout(0);
+        check_errors(&device, "The GPU shader could not be compiled")?;
+
+        let column_x: Vec<f32> = (0..columns as isize)
+            .map(|c| input[0].get_x_from_column(c) as f32)
+            .collect();
+
+        let mut output_rows: Vec<Vec<f64>> = Vec::with_capacity(rows);
+        let mut strip_start = 0usize;
+        while strip_start < rows {
+            let n = strip_rows.min(rows - strip_start);
+
+            let mut data = vec![0f32; num_inputs * n * columns];
+            let mut any_nodata = vec![false; n * columns];
+            for (i, raster) in input.iter().enumerate() {
+                let nodata = raster.configs.nodata;
+                for r in 0..n {
+                    for c in 0..columns {
+                        let value = raster.get_value((strip_start + r) as isize, c as isize);
+                        if is_nodata(value, nodata) {
+                            any_nodata[r * columns + c] = true;
+                        }
+                        data[(i * n + r) * columns + c] = value as f32;
+                    }
+                }
+            }
+            data.extend((strip_start..strip_start + n).map(|r| input[0].get_y_from_row(r as isize) as f32));
+            data.extend_from_slice(&column_x);
+            let params = [n as u32, columns as u32, strip_start as u32, 0u32];
+
+            device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+            device.push_error_scope(wgpu::ErrorFilter::Validation);
+            let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&params),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+            let input_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&data),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+            let out_size = (n * columns * 4) as u64;
+            let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: out_size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            });
+            let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: out_size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: input_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: output_buffer.as_entire_binding(),
+                    },
+                ],
+            });
+
+            let mut encoder =
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: None,
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(&pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups(
+                    ((columns + 15) / 16) as u32,
+                    ((n + 15) / 16) as u32,
+                    1,
+                );
+            }
+            encoder.copy_buffer_to_buffer(&output_buffer, 0, &staging_buffer, 0, out_size);
+            queue.submit(Some(encoder.finish()));
+            check_errors(&device, "The GPU computation failed")?;
+
+            let slice = staging_buffer.slice(..);
+            let (tx, rx) = mpsc::channel();
+            slice.map_async(wgpu::MapMode::Read, move |result| {
+                let _ = tx.send(result);
+            });
+            device.poll(wgpu::Maintain::Wait);
+            match rx.recv() {
+                Ok(Ok(())) => {}
+                _ => return Err("The GPU output could not be read".to_string()),
+            }
+            {
+                let mapped = slice.get_mapped_range();
+                let values: &[f32] = bytemuck::cast_slice(&mapped);
+                for r in 0..n {
+                    let row_data = (r * columns..(r + 1) * columns)
+                        .map(|idx| {
+                            if any_nodata[idx] && !statement_contains_nodata {
+                                out_nodata
+                            } else {
+                                values[idx] as f64
+                            }
+                        })
+                        .collect();
+                    output_rows.push(row_data);
+                }
+            }
+            staging_buffer.unmap();
+
+            strip_start += n;
+        }
+
+        Ok(output_rows)
+    }
+
+    /// Pops the validation and out-of-memory error scopes pushed before a block of GPU work.
+    fn check_errors(device: &wgpu::Device, msg: &str) -> Result<(), String> {
+        let validation = pollster::block_on(device.pop_error_scope());
+        let out_of_memory = pollster::block_on(device.pop_error_scope());
+        match validation.or(out_of_memory) {
+            Some(e) => Err(format!("{} ({})", msg, e)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{literal, translate};
+    use whitebox_raster::{Raster, RasterConfigs};
+
+    fn input(num_inputs: usize) -> Vec<Raster> {
+        let configs = RasterConfigs {
+            rows: 2,
+            columns: 3,
+            north: 20.0,
+            south: 0.0,
+            east: 30.0,
+            west: 0.0,
+            resolution_x: 10.0,
+            resolution_y: 10.0,
+            ..Default::default()
+        };
+        (0..num_inputs)
+            .map(|_| Raster::initialize_using_config("test.tif", &configs))
+            .collect()
+    }
+
+    #[test]
+    fn test_translate() {
+        let input = input(2);
+        assert_eq!(
+            translate("value0 > 2500.0", &input).unwrap(),
+            format!("wb_bool(input[0u * cells + idx] > {})", literal(2500.0))
+        );
+        assert_eq!(
+            translate("(value0 >= 25) && (value1 <= 75)", &input).unwrap(),
+            format!(
+                "wb_and(wb_bool(input[0u * cells + idx] >= {}), wb_bool(input[1u * cells + idx] <= {}))",
+                literal(25.0),
+                literal(75.0)
+            )
+        );
+        let expression = translate("tan(value0 * pi() / 180.0) + round(columnx) - rows", &input).unwrap();
+        assert!(expression.contains("tan("));
+        assert!(expression.contains("wb_round("));
+        assert!(expression.contains("input[2u * cells + params.rows + id.x]"));
+        assert!(expression.contains(&literal(2.0)));
+        // constant statements are folded by fasteval
+        assert_eq!(translate("2 ^ 3", &input).unwrap(), literal(8.0));
+    }
+
+    #[test]
+    fn test_translate_unsupported() {
+        let input = input(1);
+        // statements that can only be evaluated on the CPU
+        assert!(translate("value1 + 1", &input).is_err());
+        assert!(translate("undefined_var * 2", &input).is_err());
+        assert!(translate("custom(value0)", &input).is_err());
+        assert!(translate("print(\"x\", value0)", &input).is_err());
+        assert!(translate("value0 +", &input).is_err());
+    }
+}
@@ -5,6 +5,8 @@ Last Modified: 21/07/2021
 License: MIT
 */
 
+mod gpu;
+
 use std::collections::{BTreeMap, HashSet};
 use std::env;
 use std::f64;
@@ -15,8 +17,8 @@ use std::time::Instant;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
-use num_cpus;
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::errors::ErrorCause;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 use whitebox_raster::*;
 // use v_eval::{Value, Eval};
 use fasteval;
@@ -126,15 +128,21 @@ use fasteval;
 /// unless a NoData grid cell value allows the statement to evaluate to True (i.e. the mathematical expression 
 /// includes the `nodata` value).
 ///
+/// When WhiteboxTools is built with GPU support and the tool is run with the `--compute=gpu` flag, the
+/// statement is evaluated on the GPU, in single precision. Statements that cannot be evaluated on the
+/// GPU, e.g. because they use an unrecognized variable or function, are evaluated on the CPU, as they
+/// are when no GPU is available.
+///
 /// # See Also
 /// `ConditionalEvaluation`
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     if args[1].trim() == "run" {
-        match run(&args) {
-            Ok(_) => {}
-            Err(e) => panic!("{:?}", e),
+        if let Err(e) = run(&args) {
+            // exit with the code of the cause, which the parent process reports
+            eprintln!("Error: {}", e);
+            std::process::exit(ErrorCause::of(&e).exit_code());
         }
     }
 
@@ -206,7 +214,6 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     if !working_directory.is_empty() && !working_directory.ends_with(&sep) {
         working_directory += &sep;
     }
-    let max_procs = configurations.max_procs;
 
     // read the arguments
     let mut statement = String::new();
@@ -260,9 +267,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let start = Instant::now();
 
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    output_file = resolve_path(&output_file, &working_directory);
 
     // We need to find and read the input files
     let mut delimiter = "\"";
@@ -304,9 +309,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         if !input_files[i].contains(".") {
             input_files[i].push_str(".tif");
         }
-        if !input_files[i].contains(&sep) && !input_files[i].contains("/") {
-            input_files[i] = format!("{}{}", working_directory, input_files[i]);
-        }
+        input_files[i] = resolve_path(&input_files[i], &working_directory);
     }
 
     ////////////////////////////
@@ -325,9 +328,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         if !input_files[i].trim().is_empty() {
             // quality control on the image file name.
             let mut input_file = input_files[i].trim().to_owned();
-            if !input_file.contains(&sep) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_input_path(&input_file, &working_directory)?;
 
             // read the image
             input_raster.push(Raster::new(&input_file, "r")?);
@@ -344,8 +345,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                 if input_raster[i].configs.rows as isize != rows
                     || input_raster[i].configs.columns as isize != columns
                 {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                        "All input images must share the same dimensions (rows and columns) and spatial extent."));
+                    return Err(ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
                 }
             }
         } else {
@@ -408,59 +408,68 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     let out_nodata = -32_768f64;
     output.configs.nodata = out_nodata;
     
-    let mut num_procs = num_cpus::get() as isize;
-    if max_procs > 0 && max_procs < num_procs {
-        num_procs = max_procs;
-    }
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
+
+    let gpu_rows = if configurations.compute.trim().to_lowercase() == "gpu" {
+        gpu::evaluate(&statement, &input_raster, statement_contains_nodata, out_nodata, configurations.verbose_mode)
+    } else {
+        None
+    };
 
     let input_raster = Arc::new(input_raster);
     let nodata = Arc::new(nodata);
     // calculate the number of inflowing cells
     let (tx, rx) = mpsc::channel();
-    for tid in 0..num_procs {
-        let tx = tx.clone();
-        let statement = statement.clone();
-        let input_raster = input_raster.clone();
-        let nodata = nodata.clone();
-        thread::spawn(move || {
-            let mut value: f64;
-            let mut is_nodata: bool;
-            let mut map : BTreeMap<String, f64> = BTreeMap::new();
-            map.insert("rows".to_string(), rows as f64);
-            map.insert("columns".to_string(), columns as f64);
-            map.insert("north".to_string(), input_raster[0].configs.north);
-            map.insert("south".to_string(), input_raster[0].configs.south);
-            map.insert("east".to_string(), input_raster[0].configs.east);
-            map.insert("west".to_string(), input_raster[0].configs.west);
-            map.insert("cellsizex".to_string(), input_raster[0].configs.resolution_x);
-            map.insert("cellsizey".to_string(), input_raster[0].configs.resolution_y);
-            map.insert("cellsize".to_string(), (input_raster[0].configs.resolution_x + input_raster[0].configs.resolution_y)/2.0);
-
-            for row in (0..rows).filter(|r| r % num_procs == tid) {
-                let mut data: Vec<f64> = vec![out_nodata; columns as usize];
-                map.insert("row".to_string(), row as f64);
-                map.insert("rowy".to_string(), input_raster[0].get_y_from_row(row));
-                for col in 0..columns {
-                    map.insert("column".to_string(), col as f64);
-                    map.insert("columnx".to_string(), input_raster[0].get_x_from_column(col));
-                    is_nodata = false;
-                    for i in 0..num_inputs {
-                        value = input_raster[i].get_value(row, col);
-                        if value == nodata[i] { is_nodata = true; }
-                        map.insert(format!("value{}", i), value);
-                    }
-                    if !is_nodata || statement_contains_nodata {
-                        let ret = fasteval::ez_eval(&statement, &mut map);
-                        if ret.is_ok() {
-                            value = ret.unwrap();
-                            data[col as usize] = value;
+    if let Some(gpu_rows) = gpu_rows {
+        for (row, data) in gpu_rows.into_iter().enumerate() {
+            tx.send((row as isize, data)).expect("Error sending data to thread.");
+        }
+    } else {
+        for tid in 0..num_procs {
+            let tx = tx.clone();
+            let statement = statement.clone();
+            let input_raster = input_raster.clone();
+            let nodata = nodata.clone();
+            thread::spawn(move || {
+                let mut value: f64;
+                let mut any_nodata: bool;
+                let mut map : BTreeMap<String, f64> = BTreeMap::new();
+                map.insert("rows".to_string(), rows as f64);
+                map.insert("columns".to_string(), columns as f64);
+                map.insert("north".to_string(), input_raster[0].configs.north);
+                map.insert("south".to_string(), input_raster[0].configs.south);
+                map.insert("east".to_string(), input_raster[0].configs.east);
+                map.insert("west".to_string(), input_raster[0].configs.west);
+                map.insert("cellsizex".to_string(), input_raster[0].configs.resolution_x);
+                map.insert("cellsizey".to_string(), input_raster[0].configs.resolution_y);
+                map.insert("cellsize".to_string(), (input_raster[0].configs.resolution_x + input_raster[0].configs.resolution_y)/2.0);
+
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data: Vec<f64> = vec![out_nodata; columns as usize];
+                    map.insert("row".to_string(), row as f64);
+                    map.insert("rowy".to_string(), input_raster[0].get_y_from_row(row));
+                    for col in 0..columns {
+                        map.insert("column".to_string(), col as f64);
+                        map.insert("columnx".to_string(), input_raster[0].get_x_from_column(col));
+                        any_nodata = false;
+                        for i in 0..num_inputs {
+                            value = input_raster[i].get_value(row, col);
+                            if is_nodata(value, nodata[i]) { any_nodata = true; }
+                            map.insert(format!("value{}", i), value);
+                        }
+                        if !any_nodata || statement_contains_nodata {
+                            let ret = fasteval::ez_eval(&statement, &mut map);
+                            if ret.is_ok() {
+                                value = ret.unwrap();
+                                data[col as usize] = value;
+                            }
                         }
                     }
+                    tx.send((row, data))
+                        .expect("Error sending data to thread.");
                 }
-                tx.send((row, data))
-                    .expect("Error sending data to thread.");
-            }
-        });
+            });
+        }
     }
 
     let mut is_float_data = false;
@@ -469,7 +478,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         
         if !is_float_data {
             for i in 0..data.len() {
-                if data[i] != nodata[0] {
+                if !is_nodata(data[i], nodata[0]) {
                     if data[i].round() != data[i] {
                         is_float_data = true;
                         break;
@@ -16,10 +16,9 @@ use std::time::Instant;
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
-use num_cpus;
 use rand::Rng;
 use whitebox_common::structures::{Array2D};
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 use whitebox_raster::*;
 
 /// This tool is used to generate a flow accumulation grid (i.e. contributing area) using the Fairfield and Leymarie (1991) 
@@ -216,17 +215,11 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
         println!("{}", "*".repeat(welcome_len));
     }
 
-    let sep: String = path::MAIN_SEPARATOR.to_string();
-
     let mut progress: usize;
     let mut old_progress: usize = 1;
 
-    if !input_file.contains(&sep) && !input_file.contains("/") {
-        input_file = format!("{}{}", working_directory, input_file);
-    }
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    input_file = resolve_input_path(&input_file, &working_directory)?;
+    output_file = resolve_path(&output_file, &working_directory);
 
     if configurations.verbose_mode {
         println!("Reading data...")
@@ -245,12 +238,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     // -2 indicates NoData, -1 indicates no downslope neighbour, 0-7 indicate flow to one neighbour.
     let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -2, -2)?;
     let mut interior_pit_found = false;
-    let mut num_procs = num_cpus::get() as isize;
-    let configs = whitebox_common::configs::get_configs()?;
-    let max_procs = configs.max_procs;
-    if max_procs > 0 && max_procs < num_procs {
-        num_procs = max_procs;
-    }
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
 
     if !pntr_input {
        let (tx, rx) = mpsc::channel();
@@ -268,12 +256,12 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                     let mut data = vec![-2i8; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             let mut dir = 0;
                             let mut max_slope = f64::MIN;
                             for i in 0..8 {
                                 z_n = input[(row + d_y[i], col + d_x[i])];
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     slope = match i {
                                         1 | 3 | 5 | 7 => (z - z_n),
                                         _ => (z - z_n) / (2f64 - rng.gen_range(0f64, 1f64)), //between.ind_sample(&mut rng)),
@@ -354,7 +342,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                     let mut data: Vec<i8> = vec![-2i8; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             if z > 0f64 {
                                 data[col as usize] = pntr_matches[z as usize];
                             } else {
@@ -362,7 +350,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
                                 // is this no-flow cell interior?
                                 neighbouring_nodata = false;
                                 for i in 0..8 {
-                                    if input.get_value(row + dy[i], col + dx[i]) == nodata {
+                                    if is_nodata(input.get_value(row + dy[i], col + dx[i]), nodata) {
                                         neighbouring_nodata = true;
                                     }
                                 }
@@ -12,7 +12,7 @@ use std::path;
 use std::str;
 use std::time::Instant;
 use whitebox_common::structures::{Point2D};
-use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_common::utils::{get_formatted_elapsed_time, resolve_input_path, resolve_path};
 use whitebox_vector::{AttributeField, FieldData, FieldDataType, Shapefile, ShapefileGeometry, ShapeType};
 
 /// This tool can be used to divide longer vector lines (`--input`) into segments of a maximum specified length
@@ -162,13 +162,9 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let start = Instant::now();
 
-    if !input_file.contains(&sep) && !input_file.contains("/") {
-        input_file = format!("{}{}", working_directory, input_file);
-    }
+    input_file = resolve_input_path(&input_file, &working_directory)?;
 
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    output_file = resolve_path(&output_file, &working_directory);
 
     let input = Shapefile::read(&input_file)?;
 
@@ -1,13 +1,11 @@
 /* 
 Authors: Prof. John Lindsay
 Created: 23/02/2022
-Last Modified: 06/05/2022
+Last Modified: 16/10/2026
 License: MIT
 */
-extern crate tsp_rs;
+mod tour;
 
-use tsp_rs::Tour;
-use tsp_rs::Metrizable;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
@@ -17,15 +15,16 @@ use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread;
 use std::time::Instant;
+use tour::{Metrizable, Tour};
 use whitebox_common::structures::Point2D;
-use whitebox_common::utils::{haversine_distance, get_formatted_elapsed_time};
+use whitebox_common::utils::{haversine_distance, get_formatted_elapsed_time, resolve_input_path, resolve_path};
 use whitebox_vector::{AttributeField, FieldData, FieldDataType, Shapefile, ShapefileGeometry, ShapeType};
 
 /// This tool finds approximate solutions to [travelling salesman problems](https://en.wikipedia.org/wiki/Travelling_salesman_problem), 
 /// the goal of which is to identify the shortest route connecting a set of locations. The tool uses
-/// an algorithm that applies a [2-opt heuristic](https://en.wikipedia.org/wiki/2-opt) and a 
-/// [3-opt](https://en.wikipedia.org/wiki/3-opt) heuristic as a fall-back if the initial approach 
-/// takes too long. The user must specify the names of the input points vector (`--input`) and output lines
+/// an algorithm that applies a [2-opt heuristic](https://en.wikipedia.org/wiki/2-opt) and the Or-opt
+/// form of the [3-opt](https://en.wikipedia.org/wiki/3-opt) heuristic, with random perturbations of the
+/// route to escape from local optima. The user must specify the names of the input points vector (`--input`) and output lines
 /// vector file (`--output`), as well as the duration, in seconds, over which the algorithm is allowed to search
 /// for improved solutions (`--duration`). The tool works in parallel to find more optimal solutions.
 fn main() {
@@ -107,11 +106,7 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
     if !working_directory.is_empty() && !working_directory.ends_with(&sep) {
         working_directory += &sep;
     }
-    let max_procs = configurations.max_procs;
-    let mut num_procs = thread::available_parallelism()?.get();
-    if max_procs > 0 && (max_procs as usize) < num_procs {
-        num_procs = max_procs as usize;
-    }
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads();
 
     // read the arguments
     let mut input_file = String::new();
@@ -175,13 +170,9 @@ fn run(args: &Vec<String>) -> Result<(), std::io::Error> {
 
     let start = Instant::now();
 
-    if !input_file.contains(&sep) && !input_file.contains("/") {
-        input_file = format!("{}{}", working_directory, input_file);
-    }
+    input_file = resolve_input_path(&input_file, &working_directory)?;
 
-    if !output_file.contains(&sep) && !output_file.contains("/") {
-        output_file = format!("{}{}", working_directory, output_file);
-    }
+    output_file = resolve_path(&output_file, &working_directory);
 
     let input = Shapefile::read(&input_file)?;
 
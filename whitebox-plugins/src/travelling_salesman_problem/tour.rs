@@ -0,0 +1,288 @@
+/*
+Authors: Prof. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: This replaces the tour optimization of the tsp-rs crate, which is no longer a dependency.
+Each optimization starts from a nearest-neighbour tour, from a randomly chosen location, and applies
+iterated local search: the tour is improved with 2-opt and Or-opt moves (the latter being the
+segment-insertion form of 3-opt) until neither helps, and then perturbed with a random double-bridge
+move and improved again, keeping the shortest tour found before the time is up. Because the starting
+location and the perturbations are random, parallel runs explore different tours.
+*/
+
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use std::time::{Duration, Instant};
+
+/// A location that can be visited by a tour.
+pub trait Metrizable {
+    /// Returns the cost of travelling between `self` and `other`, which must be symmetric.
+    fn cost(&self, other: &Self) -> f64;
+}
+
+/// A closed tour of a set of locations, visited in the order of `path`.
+#[derive(Debug, Clone)]
+pub struct Tour<T> {
+    pub path: Vec<T>,
+}
+
+const EPSILON: f64 = 1e-10;
+
+impl<T: Metrizable + Clone> Tour<T> {
+    /// Creates a tour visiting `path` in order.
+    pub fn from(path: &[T]) -> Tour<T> {
+        Tour {
+            path: path.to_vec(),
+        }
+    }
+
+    /// Returns the length of the closed tour.
+    pub fn tour_len(&self) -> f64 {
+        let order: Vec<usize> = (0..self.path.len()).collect();
+        self.order_len(&order)
+    }
+
+    /// Shortens the tour by iterated local search for up to `duration`.
+    pub fn optimize_kopt(&mut self, duration: Duration) {
+        let n = self.path.len();
+        if n < 4 {
+            return;
+        }
+        let deadline = Instant::now() + duration;
+        let mut rng = SmallRng::from_entropy();
+
+        let mut best = self.nearest_neighbour_order(rng.gen_range(0, n));
+        self.local_search(&mut best, deadline);
+        let mut best_len = self.order_len(&best);
+        while n >= 8 && Instant::now() < deadline {
+            let mut candidate = double_bridge(&best, &mut rng);
+            self.local_search(&mut candidate, deadline);
+            let len = self.order_len(&candidate);
+            if len < best_len - EPSILON {
+                best = candidate;
+                best_len = len;
+            }
+        }
+
+        // The first search can be cut short, but never leaves the tour longer than it began.
+        if best_len < self.tour_len() {
+            self.path = best.iter().map(|&i| self.path[i].clone()).collect();
+        }
+    }
+
+    fn cost(&self, a: usize, b: usize) -> f64 {
+        self.path[a].cost(&self.path[b])
+    }
+
+    fn order_len(&self, order: &[usize]) -> f64 {
+        let n = order.len();
+        (0..n).map(|i| self.cost(order[i], order[(i + 1) % n])).sum()
+    }
+
+    fn nearest_neighbour_order(&self, start: usize) -> Vec<usize> {
+        let n = self.path.len();
+        let mut visited = vec![false; n];
+        let mut order = Vec::with_capacity(n);
+        let mut current = start;
+        visited[current] = true;
+        order.push(current);
+        for _ in 1..n {
+            let mut next = n;
+            let mut min_cost = f64::INFINITY;
+            for (i, &v) in visited.iter().enumerate() {
+                if !v {
+                    let c = self.cost(current, i);
+                    if c < min_cost || next == n {
+                        min_cost = c;
+                        next = i;
+                    }
+                }
+            }
+            visited[next] = true;
+            order.push(next);
+            current = next;
+        }
+        order
+    }
+
+    fn local_search(&self, order: &mut Vec<usize>, deadline: Instant) {
+        while Instant::now() < deadline {
+            if !self.two_opt(order, deadline) && !self.or_opt(order, deadline) {
+                break;
+            }
+        }
+    }
+
+    // Reverses the segments whose reversal shortens the tour. Returns true if any were.
+    fn two_opt(&self, order: &mut [usize], deadline: Instant) -> bool {
+        let n = order.len();
+        let mut improved = false;
+        for i in 0..n - 2 {
+            if Instant::now() >= deadline {
+                break;
+            }
+            for j in i + 2..n {
+                if i == 0 && j == n - 1 {
+                    continue; // the two edges share a location
+                }
+                let (a, b) = (order[i], order[i + 1]);
+                let (c, d) = (order[j], order[(j + 1) % n]);
+                let delta = self.cost(a, c) + self.cost(b, d) - self.cost(a, b) - self.cost(c, d);
+                if delta < -EPSILON {
+                    order[i + 1..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+        improved
+    }
+
+    // Moves segments of up to three locations, in either direction, to between the pair of
+    // neighbouring locations where they shorten the tour most. Returns true if any were moved.
+    fn or_opt(&self, order: &mut Vec<usize>, deadline: Instant) -> bool {
+        let n = order.len();
+        let mut improved = false;
+        for seg_len in 1..=3 {
+            let mut i = 0;
+            while i + seg_len <= n {
+                if Instant::now() >= deadline {
+                    return improved;
+                }
+                let prev = order[(i + n - 1) % n];
+                let first = order[i];
+                let last = order[i + seg_len - 1];
+                let next = order[(i + seg_len) % n];
+                let removal_gain =
+                    self.cost(prev, first) + self.cost(last, next) - self.cost(prev, next);
+
+                // the best pair of neighbours (p, q), outside of the segment, to insert it between
+                let mut best: Option<(usize, bool, f64)> = None;
+                for k in 0..n {
+                    let (p, q) = (order[k], order[(k + 1) % n]);
+                    let in_segment = |m: usize| m >= i && m < i + seg_len;
+                    if in_segment(k) || in_segment((k + 1) % n) || p == prev {
+                        continue;
+                    }
+                    let forward = self.cost(p, first) + self.cost(last, q) - self.cost(p, q);
+                    let reversed = self.cost(p, last) + self.cost(first, q) - self.cost(p, q);
+                    let (added, reverse) = if reversed < forward {
+                        (reversed, true)
+                    } else {
+                        (forward, false)
+                    };
+                    if added < removal_gain - EPSILON && best.is_none_or(|b| added < b.2) {
+                        best = Some((p, reverse, added));
+                    }
+                }
+
+                if let Some((p, reverse, _)) = best {
+                    let mut segment: Vec<usize> = order.drain(i..i + seg_len).collect();
+                    if reverse {
+                        segment.reverse();
+                    }
+                    let k = order.iter().position(|&m| m == p).unwrap_or(0);
+                    order.splice(k + 1..k + 1, segment);
+                    improved = true;
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        improved
+    }
+}
+
+// Reconnects four segments of the tour in a different order, a move that 2-opt and Or-opt can't
+// easily undo, to escape a local optimum.
+fn double_bridge(order: &[usize], rng: &mut SmallRng) -> Vec<usize> {
+    let n = order.len();
+    let mut cuts = [
+        rng.gen_range(1, n),
+        rng.gen_range(1, n),
+        rng.gen_range(1, n),
+    ];
+    cuts.sort_unstable();
+    let [a, b, c] = cuts;
+    let mut result = Vec::with_capacity(n);
+    result.extend_from_slice(&order[..a]);
+    result.extend_from_slice(&order[b..c]);
+    result.extend_from_slice(&order[a..b]);
+    result.extend_from_slice(&order[c..]);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Metrizable, Tour};
+    use std::time::Duration;
+
+    #[derive(Clone, Debug)]
+    struct Location(f64, f64);
+
+    impl Metrizable for Location {
+        fn cost(&self, other: &Location) -> f64 {
+            (self.0 - other.0).hypot(self.1 - other.1)
+        }
+    }
+
+    // The berlin52 instance of TSPLIB, whose optimal tour has a length of 7542.
+    const BERLIN52: [(f64, f64); 52] = [
+        (565.0, 575.0), (25.0, 185.0), (345.0, 750.0), (945.0, 685.0),
+        (845.0, 655.0), (880.0, 660.0), (25.0, 230.0), (525.0, 1000.0),
+        (580.0, 1175.0), (650.0, 1130.0), (1605.0, 620.0), (1220.0, 580.0),
+        (1465.0, 200.0), (1530.0, 5.0), (845.0, 680.0), (725.0, 370.0),
+        (145.0, 665.0), (415.0, 635.0), (510.0, 875.0), (560.0, 365.0),
+        (300.0, 465.0), (520.0, 585.0), (480.0, 415.0), (835.0, 625.0),
+        (975.0, 580.0), (1215.0, 245.0), (1320.0, 315.0), (1250.0, 400.0),
+        (660.0, 180.0), (410.0, 250.0), (420.0, 555.0), (575.0, 665.0),
+        (1150.0, 1160.0), (700.0, 580.0), (685.0, 595.0), (685.0, 610.0),
+        (770.0, 610.0), (795.0, 645.0), (720.0, 635.0), (760.0, 650.0),
+        (475.0, 960.0), (95.0, 260.0), (875.0, 920.0), (700.0, 500.0),
+        (555.0, 815.0), (830.0, 485.0), (1170.0, 65.0), (830.0, 610.0),
+        (605.0, 625.0), (595.0, 360.0), (1340.0, 725.0), (1740.0, 245.0),
+    ];
+
+    fn optimized_len(locations: &[Location]) -> f64 {
+        let mut tour = Tour::from(locations);
+        tour.optimize_kopt(Duration::from_secs(1));
+        tour.tour_len()
+    }
+
+    #[test]
+    fn test_berlin52_no_longer_than_tsp_rs() {
+        // tsp-rs 0.1.0, which this replaces, found tours of 7732 to 8371 in one second
+        let locations: Vec<Location> = BERLIN52.iter().map(|&(x, y)| Location(x, y)).collect();
+        let len = optimized_len(&locations);
+        assert!(len >= 7542.0 - 1e-6);
+        assert!(len <= 7732.0, "tour length {} exceeds that of tsp-rs", len);
+    }
+
+    #[test]
+    fn test_grid_no_longer_than_tsp_rs() {
+        // a shuffled 10 x 10 unit grid, whose optimal tour has a length of 100; tsp-rs 0.1.0
+        // found tours of 103.7 to 107.9 in one second
+        let locations: Vec<Location> = (0..100)
+            .map(|i| {
+                let k = (i * 37) % 100;
+                Location((k / 10) as f64, (k % 10) as f64)
+            })
+            .collect();
+        let len = optimized_len(&locations);
+        assert!(len >= 100.0 - 1e-6);
+        assert!(len <= 103.7, "tour length {} exceeds that of tsp-rs", len);
+    }
+
+    #[test]
+    fn test_tour_visits_every_location_once() {
+        let locations: Vec<Location> = BERLIN52.iter().map(|&(x, y)| Location(x, y)).collect();
+        let mut tour = Tour::from(&locations);
+        tour.optimize_kopt(Duration::from_millis(100));
+        assert_eq!(tour.path.len(), locations.len());
+        for l in &locations {
+            let count = tour.path.iter().filter(|p| p.0 == l.0 && p.1 == l.1).count();
+            assert_eq!(count, 1);
+        }
+    }
+}
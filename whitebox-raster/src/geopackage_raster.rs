@@ -0,0 +1,1002 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Reading and writing rasters stored as GeoPackage (.gpkg) tile pyramids, using the OGC
+2D gridded coverage extension (gpkg_2d_gridded_coverage), which is the form in which elevation
+data are distributed as GeoPackages. Integer coverages store their values in 16-bit greyscale
+PNG tiles, with a scale and offset that transform the stored values into data values, and
+floating-point coverages store them in 32-bit floating-point TIFF tiles.
+
+Reading uses the first gridded coverage listed in the gpkg_contents table, at its most detailed
+zoom level, cropped to the extent given in gpkg_contents. Missing tiles are read as nodata.
+Imagery tile tables (e.g. of JPEG tiles), which hold no data values, are not supported.
+
+Writing creates a new GeoPackage holding a single coverage, named after the file, with one zoom
+level of 256 x 256 tiles, which covers the raster from its north-west corner; tiles without any
+valid cells are omitted. Rasters of integer values are written as PNG tiles when their values,
+and the nodata value, span no more than the 65,536 values of a 16-bit tile, and otherwise as
+LZW-compressed TIFF tiles. Because the database is written without indices, the tables do not
+declare the UNIQUE and non-rowid PRIMARY KEY constraints of the specification.
+*/
+
+use super::*;
+use crate::geotiff::compression::{encode_block, undo_predictor, TiffCompression};
+use crate::geotiff::tiff_consts::*;
+use crate::png::{self, PNG_SIGNATURE};
+use miniz_oxide::inflate::decompress_to_vec_zlib;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+use whitebox_common::utils::{Endianness, SqlValue, SqliteReader, SqliteWriter};
+
+/// The application ID of GeoPackage files, i.e. 'GPKG'.
+const GPKG_APPLICATION_ID: u32 = 0x4750_4B47;
+/// The version of the GeoPackage specification (1.2) to which the files that are written conform.
+const GPKG_USER_VERSION: u32 = 10_200;
+const COVERAGE_DATA_TYPE: &str = "2d-gridded-coverage";
+const COVERAGE_EXTENSION: &str = "gpkg_2d_gridded_coverage";
+const COVERAGE_DEFINITION: &str = "http://docs.opengeospatial.org/is/17-066r1/17-066r1.html";
+const TILE_SIZE: usize = 256;
+/// The identifier given to a coordinate reference system that has a WKT definition, but no
+/// EPSG code.
+const CUSTOM_SRS_ID: i64 = 100_000;
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+fn as_f64(value: &SqlValue) -> Option<f64> {
+    match value {
+        SqlValue::Integer(v) => Some(*v as f64),
+        SqlValue::Real(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn as_i64(value: &SqlValue) -> Option<i64> {
+    match value {
+        SqlValue::Integer(v) => Some(*v),
+        SqlValue::Real(v) if v.fract() == 0f64 => Some(*v as i64),
+        _ => None,
+    }
+}
+
+fn as_str(value: &SqlValue) -> &str {
+    match value {
+        SqlValue::Text(s) => s,
+        _ => "",
+    }
+}
+
+/// The rows of a table, with their values looked up by column name.
+struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<SqlValue>>,
+}
+
+impl Table {
+    fn read(db: &mut SqliteReader, name: &str) -> Result<Table, Error> {
+        Ok(Table {
+            columns: db.table_columns(name)?,
+            rows: db.read_table(name)?,
+        })
+    }
+
+    fn get<'a>(&self, row: &'a [SqlValue], column: &str) -> &'a SqlValue {
+        match self
+            .columns
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case(column))
+        {
+            Some(i) => &row[i],
+            None => &SqlValue::Null,
+        }
+    }
+
+    /// Returns the rows whose `column` holds the text `value`, matched case-insensitively.
+    fn rows_with<'a>(
+        &'a self,
+        column: &'a str,
+        value: &'a str,
+    ) -> impl Iterator<Item = &'a Vec<SqlValue>> {
+        self.rows
+            .iter()
+            .filter(move |row| as_str(self.get(row, column)).eq_ignore_ascii_case(value))
+    }
+}
+
+pub fn read_geopackage(
+    file_name: &String,
+    configs: &mut RasterConfigs,
+    data: &mut Vec<f64>,
+) -> Result<(), Error> {
+    let mut db = SqliteReader::open(file_name)?;
+    let contents = Table::read(&mut db, "gpkg_contents")?;
+    let content = contents
+        .rows_with("data_type", COVERAGE_DATA_TYPE)
+        .next()
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "The GeoPackage {} does not contain a gridded coverage (e.g. of elevations).",
+                    file_name
+                ),
+            )
+        })?;
+    let table_name = as_str(contents.get(content, "table_name")).to_string();
+    let identifier = as_str(contents.get(content, "identifier"));
+    configs.title = if identifier.is_empty() {
+        table_name.clone()
+    } else {
+        identifier.to_string()
+    };
+    let description = as_str(contents.get(content, "description"));
+    if !description.is_empty() {
+        configs.metadata.push(description.to_string());
+    }
+
+    let matrix_sets = Table::read(&mut db, "gpkg_tile_matrix_set")?;
+    let matrix_set = matrix_sets
+        .rows_with("table_name", &table_name)
+        .next()
+        .ok_or_else(|| invalid("The GeoPackage has no tile matrix set for its coverage."))?;
+    let bounds = |table: &Table, row: &[SqlValue]| {
+        (
+            as_f64(table.get(row, "min_x")),
+            as_f64(table.get(row, "min_y")),
+            as_f64(table.get(row, "max_x")),
+            as_f64(table.get(row, "max_y")),
+        )
+    };
+    let tms_bounds = match bounds(&matrix_sets, matrix_set) {
+        (Some(min_x), Some(min_y), Some(max_x), Some(max_y)) => (min_x, min_y, max_x, max_y),
+        _ => {
+            return Err(invalid(
+                "The tile matrix set of the GeoPackage has no extent.",
+            ))
+        }
+    };
+    let (tms_min_x, tms_max_y) = (tms_bounds.0, tms_bounds.3);
+
+    // the most detailed zoom level
+    let matrices = Table::read(&mut db, "gpkg_tile_matrix")?;
+    let matrix = matrices
+        .rows_with("table_name", &table_name)
+        .max_by_key(|row| as_i64(matrices.get(row, "zoom_level")).unwrap_or(i64::MIN))
+        .ok_or_else(|| invalid("The GeoPackage has no tile matrix for its coverage."))?;
+    let zoom_level = as_i64(matrices.get(matrix, "zoom_level")).unwrap_or(0);
+    let tile_width = as_i64(matrices.get(matrix, "tile_width")).unwrap_or(0);
+    let tile_height = as_i64(matrices.get(matrix, "tile_height")).unwrap_or(0);
+    let pixel_x_size = as_f64(matrices.get(matrix, "pixel_x_size")).unwrap_or(0f64);
+    let pixel_y_size = as_f64(matrices.get(matrix, "pixel_y_size")).unwrap_or(0f64);
+    if tile_width <= 0 || tile_height <= 0 || pixel_x_size <= 0f64 || pixel_y_size <= 0f64 {
+        return Err(invalid("The tile matrix of the GeoPackage is invalid."));
+    }
+
+    let coverages = Table::read(&mut db, "gpkg_2d_gridded_coverage_ancillary")?;
+    let coverage = coverages
+        .rows_with("tile_matrix_set_name", &table_name)
+        .next()
+        .ok_or_else(|| invalid("The GeoPackage has no ancillary data for its coverage."))?;
+    let is_float = as_str(coverages.get(coverage, "datatype")).eq_ignore_ascii_case("float");
+    let scale = as_f64(coverages.get(coverage, "scale")).unwrap_or(1f64);
+    let offset = as_f64(coverages.get(coverage, "offset")).unwrap_or(0f64);
+    let data_null = as_f64(coverages.get(coverage, "data_null"));
+    let uom = as_str(coverages.get(coverage, "uom"));
+    if !uom.is_empty() {
+        configs.z_units = uom.to_string();
+    }
+
+    // the scale and offset of individual tiles, which are applied ahead of those of the coverage
+    let mut tile_transforms = HashMap::new();
+    if db.has_table("gpkg_2d_gridded_tile_ancillary") {
+        let tiles = Table::read(&mut db, "gpkg_2d_gridded_tile_ancillary")?;
+        for row in tiles.rows_with("tpudt_name", &table_name) {
+            if let Some(id) = as_i64(tiles.get(row, "tpudt_id")) {
+                tile_transforms.insert(
+                    id,
+                    (
+                        as_f64(tiles.get(row, "scale")).unwrap_or(1f64),
+                        as_f64(tiles.get(row, "offset")).unwrap_or(0f64),
+                    ),
+                );
+            }
+        }
+    }
+
+    // the extent of the data, snapped to the grid of the tile matrix
+    let (min_x, min_y, max_x, max_y) = match bounds(&contents, content) {
+        (Some(min_x), Some(min_y), Some(max_x), Some(max_y)) => (min_x, min_y, max_x, max_y),
+        _ => tms_bounds,
+    };
+    let column_offset = ((min_x - tms_min_x) / pixel_x_size).round().max(0f64) as i64;
+    let row_offset = ((tms_max_y - max_y) / pixel_y_size).round().max(0f64) as i64;
+    configs.west = tms_min_x + column_offset as f64 * pixel_x_size;
+    configs.north = tms_max_y - row_offset as f64 * pixel_y_size;
+    configs.columns = ((max_x - configs.west) / pixel_x_size).round().max(0f64) as usize;
+    configs.rows = ((configs.north - min_y) / pixel_y_size).round().max(0f64) as usize;
+    if configs.rows == 0 || configs.columns == 0 {
+        return Err(invalid("The coverage of the GeoPackage is empty."));
+    }
+    configs.east = configs.west + configs.columns as f64 * pixel_x_size;
+    configs.south = configs.north - configs.rows as f64 * pixel_y_size;
+    configs.resolution_x = pixel_x_size;
+    configs.resolution_y = pixel_y_size;
+    configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+    configs.nodata = if is_float {
+        data_null.unwrap_or(-32768f64)
+    } else {
+        data_null.map_or(-32768f64, |v| v * scale + offset)
+    };
+    configs.data_type = if is_float || scale.fract() != 0f64 || offset.fract() != 0f64 {
+        DataType::F32
+    } else {
+        DataType::I32
+    };
+
+    let srs_id = as_i64(contents.get(content, "srs_id"))
+        .or_else(|| as_i64(matrix_sets.get(matrix_set, "srs_id")));
+    if let Some(srs_id) = srs_id {
+        let ref_systems = Table::read(&mut db, "gpkg_spatial_ref_sys")?;
+        let ref_system = ref_systems
+            .rows
+            .iter()
+            .find(|row| as_i64(ref_systems.get(row, "srs_id")) == Some(srs_id));
+        if let Some(row) = ref_system {
+            let code = as_i64(ref_systems.get(row, "organization_coordsys_id")).unwrap_or(0);
+            if as_str(ref_systems.get(row, "organization")).eq_ignore_ascii_case("epsg")
+                && code > 0
+                && code <= u16::MAX as i64
+            {
+                configs.epsg_code = code as u16;
+            }
+            let definition = as_str(ref_systems.get(row, "definition"));
+            if !definition.is_empty() && !definition.eq_ignore_ascii_case("undefined") {
+                configs.coordinate_ref_system_wkt = definition.to_string();
+            } else if configs.epsg_code > 0 {
+                configs.coordinate_ref_system_wkt = esri_wkt_from_epsg(configs.epsg_code);
+            }
+        }
+    }
+
+    let (rows, columns) = (configs.rows as i64, configs.columns as i64);
+    let nodata = configs.nodata;
+    data.clear();
+    data.resize(configs.rows * configs.columns, nodata);
+    let mut error = None;
+    db.read_rows(&table_name, |row| {
+        // the columns of a tile table are id, zoom_level, tile_column, tile_row, tile_data
+        if error.is_some() || row.len() < 5 || as_i64(&row[1]) != Some(zoom_level) {
+            return;
+        }
+        let (tile_column, tile_row) = match (as_i64(&row[2]), as_i64(&row[3])) {
+            (Some(c), Some(r)) => (c, r),
+            _ => return,
+        };
+        let tile_data = match &row[4] {
+            SqlValue::Blob(b) => b,
+            _ => return,
+        };
+        let (width, height, values) = match decode_tile(tile_data) {
+            Ok(tile) => tile,
+            Err(e) => {
+                error = Some(e);
+                return;
+            }
+        };
+        if width as i64 != tile_width || height as i64 != tile_height {
+            error = Some(invalid(
+                "A tile of the GeoPackage does not match the size of its tile matrix.",
+            ));
+            return;
+        }
+        let (tile_scale, tile_offset) = as_i64(&row[0])
+            .and_then(|id| tile_transforms.get(&id).copied())
+            .unwrap_or((1f64, 0f64));
+        let row_start = tile_row * tile_height - row_offset;
+        let column_start = tile_column * tile_width - column_offset;
+        for r in 0..height {
+            let raster_row = row_start + r as i64;
+            if raster_row < 0 || raster_row >= rows {
+                continue;
+            }
+            for c in 0..width {
+                let raster_column = column_start + c as i64;
+                if raster_column < 0 || raster_column >= columns {
+                    continue;
+                }
+                let stored = values[r * width + c];
+                if Some(stored) == data_null || stored.is_nan() {
+                    continue;
+                }
+                data[(raster_row * columns + raster_column) as usize] =
+                    (stored * tile_scale + tile_offset) * scale + offset;
+            }
+        }
+    })?;
+    match error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Decodes a PNG or TIFF tile, returning its width, height, and stored values.
+fn decode_tile(tile: &[u8]) -> Result<(usize, usize, Vec<f64>), Error> {
+    if tile.starts_with(&PNG_SIGNATURE) {
+        decode_png(tile)
+    } else if tile.starts_with(b"II*\0") || tile.starts_with(b"MM\0*") {
+        decode_tiff(tile)
+    } else {
+        Err(invalid(
+            "The GeoPackage contains a tile that is neither a PNG nor a TIFF image.",
+        ))
+    }
+}
+
+/// Decodes a greyscale PNG image, of 8 or 16 bits per sample, as used by integer coverages.
+fn decode_png(png: &[u8]) -> Result<(usize, usize, Vec<f64>), Error> {
+    let mut pos = PNG_SIGNATURE.len();
+    let mut header = None;
+    let mut compressed = vec![];
+    while pos + 8 <= png.len() {
+        let len = u32::from_be_bytes(png[pos..pos + 4].try_into().unwrap()) as usize;
+        let chunk_type = &png[pos + 4..pos + 8];
+        let chunk = png
+            .get(pos + 8..pos + 8 + len)
+            .ok_or_else(|| invalid("A PNG tile of the GeoPackage is truncated."))?;
+        match chunk_type {
+            b"IHDR" if len >= 13 => header = Some(chunk.to_vec()),
+            b"IDAT" => compressed.extend_from_slice(chunk),
+            b"IEND" => break,
+            _ => {}
+        }
+        // the chunk is followed by its CRC
+        pos += len + 12;
+    }
+    let header = header.ok_or_else(|| invalid("A PNG tile of the GeoPackage has no header."))?;
+    let width = u32::from_be_bytes(header[0..4].try_into().unwrap()) as usize;
+    let height = u32::from_be_bytes(header[4..8].try_into().unwrap()) as usize;
+    let bit_depth = header[8] as usize;
+    if header[9] != 0 || (bit_depth != 8 && bit_depth != 16) || header[12] != 0 {
+        return Err(invalid(
+            "Only non-interlaced, 8- or 16-bit greyscale PNG tiles are supported in GeoPackage coverages.",
+        ));
+    }
+    let raw = decompress_to_vec_zlib(&compressed)
+        .map_err(|_| invalid("A PNG tile of the GeoPackage could not be decompressed."))?;
+    let bpp = bit_depth / 8;
+    let row_length = width * bpp;
+    if raw.len() < height * (row_length + 1) {
+        return Err(invalid("A PNG tile of the GeoPackage is truncated."));
+    }
+    let mut pixels = vec![0u8; height * row_length];
+    let mut prior = vec![0u8; row_length];
+    for r in 0..height {
+        let filter = raw[r * (row_length + 1)];
+        let line = &raw[r * (row_length + 1) + 1..(r + 1) * (row_length + 1)];
+        let row = &mut pixels[r * row_length..(r + 1) * row_length];
+        for i in 0..row_length {
+            let a = if i >= bpp { row[i - bpp] } else { 0 };
+            let b = prior[i];
+            let c = if i >= bpp { prior[i - bpp] } else { 0 };
+            let predicted = match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return Err(invalid("A PNG tile of the GeoPackage is corrupt.")),
+            };
+            row[i] = line[i].wrapping_add(predicted);
+        }
+        prior.copy_from_slice(row);
+    }
+    let values = if bpp == 2 {
+        pixels
+            .chunks_exact(2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]) as f64)
+            .collect()
+    } else {
+        pixels.iter().map(|&b| b as f64).collect()
+    };
+    Ok((width, height, values))
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+/// Decodes a single-band, 32-bit floating-point TIFF image, as used by floating-point
+/// coverages, stored in strips or tiles, uncompressed or with LZW or DEFLATE compression.
+fn decode_tiff(tiff: &[u8]) -> Result<(usize, usize, Vec<f64>), Error> {
+    let truncated = || invalid("A TIFF tile of the GeoPackage is truncated.");
+    let endian = if tiff[0] == b'I' {
+        Endianness::LittleEndian
+    } else {
+        Endianness::BigEndian
+    };
+    let read = |pos: usize, n: usize| -> Result<u64, Error> {
+        let bytes = tiff.get(pos..pos + n).ok_or_else(truncated)?;
+        let mut v = 0u64;
+        for k in 0..n {
+            let b = if endian == Endianness::LittleEndian {
+                bytes[n - 1 - k]
+            } else {
+                bytes[k]
+            };
+            v = (v << 8) | b as u64;
+        }
+        Ok(v)
+    };
+    let ifd = read(4, 4)? as usize;
+    let mut tags: HashMap<u16, Vec<u64>> = HashMap::new();
+    for i in 0..read(ifd, 2)? as usize {
+        let entry = ifd + 2 + 12 * i;
+        let tag = read(entry, 2)? as u16;
+        let size = match read(entry + 2, 2)? {
+            3 => 2, // SHORT
+            4 => 4, // LONG
+            _ => continue,
+        };
+        let count = read(entry + 4, 4)? as usize;
+        let start = if size * count <= 4 {
+            entry + 8
+        } else {
+            read(entry + 8, 4)? as usize
+        };
+        let values = (0..count)
+            .map(|k| read(start + k * size, size))
+            .collect::<Result<Vec<u64>, Error>>()?;
+        tags.insert(tag, values);
+    }
+    let tag = |t: u16, default: Option<u64>| -> Result<u64, Error> {
+        match tags.get(&t).and_then(|v| v.first()) {
+            Some(v) => Ok(*v),
+            None => default
+                .ok_or_else(|| invalid("A TIFF tile of the GeoPackage lacks a required tag.")),
+        }
+    };
+    let width = tag(TAG_IMAGEWIDTH, None)? as usize;
+    let height = tag(TAG_IMAGELENGTH, None)? as usize;
+    let compression = tag(TAG_COMPRESSION, Some(COMPRESS_NONE as u64))? as u16;
+    let predictor = tag(TAG_PREDICTOR, Some(PREDICTOR_NONE as u64))? as u16;
+    if tag(TAG_BITSPERSAMPLE, Some(1))? != 32
+        || tag(TAG_SAMPLEFORMAT, Some(1))? != 3
+        || tag(TAG_SAMPLESPERPIXEL, Some(1))? != 1
+    {
+        return Err(invalid(
+            "Only single-band, 32-bit floating-point TIFF tiles are supported in GeoPackage coverages.",
+        ));
+    }
+    // the image is divided into strips, each the width of the image, or tiles
+    let (block_width, block_height, offsets, byte_counts) = if tags.contains_key(&TAG_TILEOFFSETS) {
+        (
+            tag(TAG_TILEWIDTH, None)? as usize,
+            tag(TAG_TILELENGTH, None)? as usize,
+            &tags[&TAG_TILEOFFSETS],
+            tags.get(&TAG_TILEBYTECOUNTS).ok_or_else(truncated)?,
+        )
+    } else {
+        (
+            width,
+            tag(TAG_ROWSPERSTRIP, Some(height as u64))?.min(height as u64) as usize,
+            tags.get(&TAG_STRIPOFFSETS).ok_or_else(truncated)?,
+            tags.get(&TAG_STRIPBYTECOUNTS).ok_or_else(truncated)?,
+        )
+    };
+    if block_width == 0 || block_height == 0 {
+        return Err(invalid("A TIFF tile of the GeoPackage is corrupt."));
+    }
+    let blocks_across = width.div_ceil(block_width);
+    let mut values = vec![f64::NAN; width * height];
+    for (b, (&offset, &count)) in offsets.iter().zip(byte_counts.iter()).enumerate() {
+        let compressed = tiff
+            .get(offset as usize..(offset + count) as usize)
+            .ok_or_else(truncated)?;
+        let block_size = block_width * block_height * 4;
+        let mut block = match compression {
+            COMPRESS_NONE => compressed.to_vec(),
+            COMPRESS_LZW => lzw_decompress(compressed, block_size)?,
+            COMPRESS_DEFLATE | COMPRESS_DEFLATEOLD => decompress_to_vec_zlib(compressed)
+                .map_err(|_| invalid("A TIFF tile of the GeoPackage could not be decompressed."))?,
+            _ => {
+                return Err(invalid(
+                    "Only uncompressed, LZW, and DEFLATE TIFF tiles are supported in GeoPackage coverages.",
+                ))
+            }
+        };
+        block.resize(block_size, 0);
+        undo_predictor(&mut block, predictor, block_width, 1, 4, endian)?;
+        let (row_start, column_start) = (
+            (b / blocks_across) * block_height,
+            (b % blocks_across) * block_width,
+        );
+        for r in 0..block_height.min(height.saturating_sub(row_start)) {
+            for c in 0..block_width.min(width.saturating_sub(column_start)) {
+                let k = (r * block_width + c) * 4;
+                let bytes: [u8; 4] = block[k..k + 4].try_into().unwrap();
+                let v = if endian == Endianness::LittleEndian {
+                    f32::from_le_bytes(bytes)
+                } else {
+                    f32::from_be_bytes(bytes)
+                };
+                values[(row_start + r) * width + column_start + c] = v as f64;
+            }
+        }
+    }
+    Ok((width, height, values))
+}
+
+fn lzw_decompress(compressed: &[u8], max_len: usize) -> Result<Vec<u8>, Error> {
+    let mut decoder = lzw::DecoderEarlyChange::new(lzw::MsbReader::new(), 8);
+    let mut buf = Vec::with_capacity(max_len);
+    let mut bytes_read = 0;
+    while bytes_read < compressed.len() && buf.len() < max_len {
+        let (len, bytes) = decoder
+            .decode_bytes(&compressed[bytes_read..])
+            .map_err(|_| invalid("A TIFF tile of the GeoPackage could not be decompressed."))?;
+        if len == 0 {
+            break;
+        }
+        bytes_read += len;
+        buf.extend_from_slice(bytes);
+    }
+    Ok(buf)
+}
+
+/// How the values of a coverage are stored in its tiles.
+#[derive(Clone, Copy, PartialEq)]
+enum TileEncoding {
+    /// 16-bit PNG tiles, holding values less the offset, with `data_null` marking nodata.
+    Png { offset: f64, data_null: f64 },
+    /// 32-bit floating-point TIFF tiles, holding the values themselves.
+    Tiff,
+}
+
+/// Chooses PNG tiles for integer values that, along with the nodata value, fit the range of
+/// a 16-bit tile, storing nodata as itself where possible.
+fn choose_encoding(r: &Raster) -> TileEncoding {
+    if !r.configs.data_type.is_integer() {
+        return TileEncoding::Tiff;
+    }
+    let nodata = r.configs.nodata;
+    let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &v in &r.data {
+        if v != nodata {
+            if v.fract() != 0f64 {
+                return TileEncoding::Tiff;
+            }
+            min = min.min(v);
+            max = max.max(v);
+        }
+    }
+    if min > max {
+        // there are no valid values
+        min = 0f64;
+        max = 0f64;
+    }
+    if nodata.fract() == 0f64 && max.max(nodata) - min.min(nodata) <= 65_535f64 {
+        let offset = min.min(nodata);
+        TileEncoding::Png {
+            offset,
+            data_null: nodata - offset,
+        }
+    } else if max - min < 65_535f64 {
+        TileEncoding::Png {
+            offset: min,
+            data_null: 65_535f64,
+        }
+    } else {
+        TileEncoding::Tiff
+    }
+}
+
+/// Encodes a 16-bit greyscale PNG image, applying the Sub filter to each row.
+fn encode_png(width: usize, height: usize, values: &[u16]) -> Vec<u8> {
+    let mut raw = Vec::with_capacity(height * (width * 2 + 1));
+    for row in values.chunks_exact(width) {
+        raw.push(1u8);
+        let mut prev = [0u8; 2];
+        for v in row {
+            let bytes = v.to_be_bytes();
+            raw.push(bytes[0].wrapping_sub(prev[0]));
+            raw.push(bytes[1].wrapping_sub(prev[1]));
+            prev = bytes;
+        }
+    }
+    // bit depth 16, colour type 0 (greyscale)
+    png::encode_png(width, height, 16, 0, &raw)
+}
+
+/// Encodes a little-endian, single-strip, LZW-compressed TIFF image of 32-bit floating-point
+/// values.
+fn encode_tiff(width: usize, height: usize, values: &[f32]) -> Result<Vec<u8>, Error> {
+    let block = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    let strip = encode_block(
+        block,
+        TiffCompression::Lzw,
+        PREDICTOR_NONE,
+        width,
+        1,
+        4,
+        Endianness::LittleEndian,
+    )?;
+    // (tag, field type, value), with field type 3 being SHORT and 4 being LONG
+    let entries: [(u16, u16, u32); 11] = [
+        (TAG_IMAGEWIDTH, 4, width as u32),
+        (TAG_IMAGELENGTH, 4, height as u32),
+        (TAG_BITSPERSAMPLE, 3, 32),
+        (TAG_COMPRESSION, 3, COMPRESS_LZW as u32),
+        (TAG_PHOTOMETRICINTERPRETATION, 3, 1), // black is zero
+        (TAG_STRIPOFFSETS, 4, 0),
+        (TAG_SAMPLESPERPIXEL, 3, 1),
+        (TAG_ROWSPERSTRIP, 4, height as u32),
+        (TAG_STRIPBYTECOUNTS, 4, strip.len() as u32),
+        (TAG_PLANARCONFIGURATION, 3, 1),
+        (TAG_SAMPLEFORMAT, 3, 3), // floating point
+    ];
+    let data_offset = 8 + 2 + 12 * entries.len() + 4;
+    let mut tiff = Vec::with_capacity(data_offset + strip.len());
+    tiff.extend_from_slice(b"II*\0");
+    tiff.extend_from_slice(&8u32.to_le_bytes());
+    tiff.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    for (tag, field_type, value) in entries {
+        let value = if tag == TAG_STRIPOFFSETS {
+            data_offset as u32
+        } else {
+            value
+        };
+        tiff.extend_from_slice(&tag.to_le_bytes());
+        tiff.extend_from_slice(&field_type.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        // a SHORT value is left-justified within the four bytes of the value field
+        tiff.extend_from_slice(&value.to_le_bytes());
+    }
+    tiff.extend_from_slice(&0u32.to_le_bytes()); // there is no next IFD
+    tiff.extend_from_slice(&strip);
+    Ok(tiff)
+}
+
+/// Returns a valid table name, based on the name of the file.
+fn coverage_table_name(short_name: &str) -> String {
+    let mut name: String = short_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) || name.starts_with("gpkg") {
+        name = format!("t_{}", name);
+    }
+    name
+}
+
+pub fn write_geopackage(r: &mut Raster) -> Result<(), Error> {
+    if r.configs.data_type == DataType::RGB24
+        || r.configs.data_type == DataType::RGB48
+        || r.configs.data_type == DataType::RGBA32
+        || r.configs.photometric_interp == PhotometricInterpretation::RGB
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Colour composite rasters cannot be written as GeoPackage gridded coverages.",
+        ));
+    }
+
+    // figure out the minimum and maximum values
+    for val in &r.data {
+        let v = *val;
+        if v != r.configs.nodata {
+            if v < r.configs.minimum {
+                r.configs.minimum = v;
+            }
+            if v > r.configs.maximum {
+                r.configs.maximum = v;
+            }
+        }
+    }
+
+    let (rows, columns) = (r.configs.rows, r.configs.columns);
+    let nodata = r.configs.nodata;
+    let table_name = coverage_table_name(&r.get_short_filename());
+    let encoding = choose_encoding(r);
+    let matrix_width = columns.div_ceil(TILE_SIZE);
+    let matrix_height = rows.div_ceil(TILE_SIZE);
+
+    // the tiles that hold valid cells, along with the statistics of their values
+    let mut tiles = vec![];
+    for tile_row in 0..matrix_height {
+        for tile_column in 0..matrix_width {
+            let (mut n, mut sum, mut sum_sqr) = (0f64, 0f64, 0f64);
+            let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+            for row in tile_row * TILE_SIZE..((tile_row + 1) * TILE_SIZE).min(rows) {
+                for column in tile_column * TILE_SIZE..((tile_column + 1) * TILE_SIZE).min(columns)
+                {
+                    let v = r.data[row * columns + column];
+                    if v != nodata {
+                        n += 1f64;
+                        sum += v;
+                        sum_sqr += v * v;
+                        min = min.min(v);
+                        max = max.max(v);
+                    }
+                }
+            }
+            if n > 0f64 {
+                let mean = sum / n;
+                let std_dev = (sum_sqr / n - mean * mean).max(0f64).sqrt();
+                tiles.push((tile_row, tile_column, min, max, mean, std_dev));
+            }
+        }
+    }
+
+    let (epsg_code, wkt) = (r.configs.epsg_code, &r.configs.coordinate_ref_system_wkt);
+    let mut ref_systems = vec![
+        vec![
+            SqlValue::Text("Undefined cartesian SRS".to_string()),
+            SqlValue::Integer(-1),
+            SqlValue::Text("NONE".to_string()),
+            SqlValue::Integer(-1),
+            SqlValue::Text("undefined".to_string()),
+            SqlValue::Text("undefined cartesian coordinate reference system".to_string()),
+        ],
+        vec![
+            SqlValue::Text("Undefined geographic SRS".to_string()),
+            SqlValue::Integer(0),
+            SqlValue::Text("NONE".to_string()),
+            SqlValue::Integer(0),
+            SqlValue::Text("undefined".to_string()),
+            SqlValue::Text("undefined geographic coordinate reference system".to_string()),
+        ],
+        vec![
+            SqlValue::Text("WGS 84 geodetic".to_string()),
+            SqlValue::Integer(4326),
+            SqlValue::Text("EPSG".to_string()),
+            SqlValue::Integer(4326),
+            SqlValue::Text(esri_wkt_from_epsg(4326)),
+            SqlValue::Text(
+                "longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid"
+                    .to_string(),
+            ),
+        ],
+    ];
+    let srs_id = if epsg_code > 0 {
+        if epsg_code != 4326 {
+            let definition = if is_specified(wkt) {
+                wkt.clone()
+            } else {
+                match esri_wkt_from_epsg(epsg_code) {
+                    s if s.starts_with("Unknown") => "undefined".to_string(),
+                    s => s,
+                }
+            };
+            ref_systems.push(vec![
+                SqlValue::Text(format!("EPSG:{}", epsg_code)),
+                SqlValue::Integer(epsg_code as i64),
+                SqlValue::Text("EPSG".to_string()),
+                SqlValue::Integer(epsg_code as i64),
+                SqlValue::Text(definition),
+                SqlValue::Null,
+            ]);
+        }
+        epsg_code as i64
+    } else if is_specified(wkt) {
+        ref_systems.push(vec![
+            SqlValue::Text("Custom SRS".to_string()),
+            SqlValue::Integer(CUSTOM_SRS_ID),
+            SqlValue::Text("NONE".to_string()),
+            SqlValue::Integer(CUSTOM_SRS_ID),
+            SqlValue::Text(wkt.clone()),
+            SqlValue::Null,
+        ]);
+        CUSTOM_SRS_ID
+    } else {
+        -1
+    };
+
+    let west = r.configs.west;
+    let north = r.configs.north;
+    let (res_x, res_y) = (r.configs.resolution_x, r.configs.resolution_y);
+    let last_change = chrono::Utc::now()
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string();
+    let description = r.configs.metadata.first().cloned().unwrap_or_default();
+
+    let mut db = SqliteWriter::create(&r.file_name)?;
+    db.set_application_id(GPKG_APPLICATION_ID);
+    db.set_user_version(GPKG_USER_VERSION);
+    db.write_table(
+        "gpkg_spatial_ref_sys",
+        "CREATE TABLE gpkg_spatial_ref_sys (srs_name TEXT NOT NULL, srs_id INTEGER NOT NULL, organization TEXT NOT NULL, organization_coordsys_id INTEGER NOT NULL, definition TEXT NOT NULL, description TEXT)",
+        ref_systems,
+    )?;
+    db.write_table(
+        "gpkg_contents",
+        "CREATE TABLE gpkg_contents (table_name TEXT NOT NULL, data_type TEXT NOT NULL, identifier TEXT, description TEXT DEFAULT '', last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')), min_x DOUBLE, min_y DOUBLE, max_x DOUBLE, max_y DOUBLE, srs_id INTEGER, CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id))",
+        vec![vec![
+            SqlValue::Text(table_name.clone()),
+            SqlValue::Text(COVERAGE_DATA_TYPE.to_string()),
+            SqlValue::Text(if r.configs.title.is_empty() {
+                table_name.clone()
+            } else {
+                r.configs.title.clone()
+            }),
+            SqlValue::Text(description),
+            SqlValue::Text(last_change),
+            SqlValue::Real(west),
+            SqlValue::Real(r.configs.south),
+            SqlValue::Real(r.configs.east),
+            SqlValue::Real(north),
+            SqlValue::Integer(srs_id),
+        ]],
+    )?;
+    db.write_table(
+        "gpkg_extensions",
+        "CREATE TABLE gpkg_extensions (table_name TEXT, column_name TEXT, extension_name TEXT NOT NULL, definition TEXT NOT NULL, scope TEXT NOT NULL)",
+        [
+            ("gpkg_2d_gridded_coverage_ancillary", None),
+            ("gpkg_2d_gridded_tile_ancillary", None),
+            (table_name.as_str(), Some("tile_data")),
+        ]
+        .iter()
+        .map(|(table, column)| {
+            vec![
+                SqlValue::Text(table.to_string()),
+                column.map_or(SqlValue::Null, |c| SqlValue::Text(c.to_string())),
+                SqlValue::Text(COVERAGE_EXTENSION.to_string()),
+                SqlValue::Text(COVERAGE_DEFINITION.to_string()),
+                SqlValue::Text("read-write".to_string()),
+            ]
+        }),
+    )?;
+    db.write_table(
+        "gpkg_tile_matrix_set",
+        "CREATE TABLE gpkg_tile_matrix_set (table_name TEXT NOT NULL, srs_id INTEGER NOT NULL, min_x DOUBLE NOT NULL, min_y DOUBLE NOT NULL, max_x DOUBLE NOT NULL, max_y DOUBLE NOT NULL, CONSTRAINT fk_gtms_table_name FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name), CONSTRAINT fk_gtms_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys (srs_id))",
+        vec![vec![
+            SqlValue::Text(table_name.clone()),
+            SqlValue::Integer(srs_id),
+            SqlValue::Real(west),
+            SqlValue::Real(north - (matrix_height * TILE_SIZE) as f64 * res_y),
+            SqlValue::Real(west + (matrix_width * TILE_SIZE) as f64 * res_x),
+            SqlValue::Real(north),
+        ]],
+    )?;
+    db.write_table(
+        "gpkg_tile_matrix",
+        "CREATE TABLE gpkg_tile_matrix (table_name TEXT NOT NULL, zoom_level INTEGER NOT NULL, matrix_width INTEGER NOT NULL, matrix_height INTEGER NOT NULL, tile_width INTEGER NOT NULL, tile_height INTEGER NOT NULL, pixel_x_size DOUBLE NOT NULL, pixel_y_size DOUBLE NOT NULL, CONSTRAINT fk_tmm_table_name FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name))",
+        vec![vec![
+            SqlValue::Text(table_name.clone()),
+            SqlValue::Integer(0),
+            SqlValue::Integer(matrix_width as i64),
+            SqlValue::Integer(matrix_height as i64),
+            SqlValue::Integer(TILE_SIZE as i64),
+            SqlValue::Integer(TILE_SIZE as i64),
+            SqlValue::Real(res_x),
+            SqlValue::Real(res_y),
+        ]],
+    )?;
+    let (datatype, offset, data_null) = match encoding {
+        TileEncoding::Png { offset, data_null } => ("integer", offset, data_null),
+        TileEncoding::Tiff => ("float", 0f64, nodata),
+    };
+    db.write_table(
+        "gpkg_2d_gridded_coverage_ancillary",
+        "CREATE TABLE gpkg_2d_gridded_coverage_ancillary (id INTEGER PRIMARY KEY, tile_matrix_set_name TEXT NOT NULL, datatype TEXT NOT NULL DEFAULT 'integer', scale REAL NOT NULL DEFAULT 1.0, offset REAL NOT NULL DEFAULT 0.0, precision REAL DEFAULT 1.0, data_null REAL, grid_cell_encoding TEXT DEFAULT 'grid-value-is-center', uom TEXT, field_name TEXT DEFAULT 'Height', quantity_definition TEXT DEFAULT 'Height', CONSTRAINT fk_g2dgtct_name FOREIGN KEY (tile_matrix_set_name) REFERENCES gpkg_tile_matrix_set (table_name), CHECK (datatype in ('integer','float')))",
+        vec![vec![
+            SqlValue::Null,
+            SqlValue::Text(table_name.clone()),
+            SqlValue::Text(datatype.to_string()),
+            SqlValue::Real(1f64),
+            SqlValue::Real(offset),
+            if datatype == "integer" {
+                SqlValue::Real(1f64)
+            } else {
+                SqlValue::Null
+            },
+            SqlValue::Real(data_null),
+            SqlValue::Text(if r.configs.pixel_is_area {
+                "grid-value-is-area".to_string()
+            } else {
+                "grid-value-is-center".to_string()
+            }),
+            if is_specified(&r.configs.z_units) {
+                SqlValue::Text(r.configs.z_units.clone())
+            } else {
+                SqlValue::Null
+            },
+            SqlValue::Text("Height".to_string()),
+            SqlValue::Text("Height".to_string()),
+        ]],
+    )?;
+    // the ids of the rows of the tile table are 1, 2, 3, ..., in the order of the tiles
+    db.write_table(
+        "gpkg_2d_gridded_tile_ancillary",
+        "CREATE TABLE gpkg_2d_gridded_tile_ancillary (id INTEGER PRIMARY KEY, tpudt_name TEXT NOT NULL, tpudt_id INTEGER NOT NULL, scale REAL NOT NULL DEFAULT 1.0, offset REAL NOT NULL DEFAULT 0.0, min REAL DEFAULT NULL, max REAL DEFAULT NULL, mean REAL DEFAULT NULL, std_dev REAL DEFAULT NULL, CONSTRAINT fk_g2dgtat_name FOREIGN KEY (tpudt_name) REFERENCES gpkg_contents(table_name))",
+        tiles.iter().enumerate().map(|(i, &(_, _, min, max, mean, std_dev))| {
+            vec![
+                SqlValue::Null,
+                SqlValue::Text(table_name.clone()),
+                SqlValue::Integer(i as i64 + 1),
+                SqlValue::Real(1f64),
+                SqlValue::Real(0f64),
+                SqlValue::Real(min),
+                SqlValue::Real(max),
+                SqlValue::Real(mean),
+                SqlValue::Real(std_dev),
+            ]
+        }),
+    )?;
+
+    let data = &r.data;
+    let mut error = None;
+    let tile_rows = tiles.iter().map(|&(tile_row, tile_column, _, _, _, _)| {
+        let value = |row: usize, column: usize| {
+            let (row, column) = (tile_row * TILE_SIZE + row, tile_column * TILE_SIZE + column);
+            if row < rows && column < columns {
+                data[row * columns + column]
+            } else {
+                nodata
+            }
+        };
+        let tile_data = match encoding {
+            TileEncoding::Png { offset, data_null } => {
+                let mut values = vec![0u16; TILE_SIZE * TILE_SIZE];
+                for row in 0..TILE_SIZE {
+                    for column in 0..TILE_SIZE {
+                        let v = value(row, column);
+                        let stored = if v == nodata { data_null } else { v - offset };
+                        values[row * TILE_SIZE + column] = stored as u16;
+                    }
+                }
+                encode_png(TILE_SIZE, TILE_SIZE, &values)
+            }
+            TileEncoding::Tiff => {
+                let mut values = vec![0f32; TILE_SIZE * TILE_SIZE];
+                for row in 0..TILE_SIZE {
+                    for column in 0..TILE_SIZE {
+                        values[row * TILE_SIZE + column] = value(row, column) as f32;
+                    }
+                }
+                encode_tiff(TILE_SIZE, TILE_SIZE, &values).unwrap_or_else(|e| {
+                    error = Some(e);
+                    vec![]
+                })
+            }
+        };
+        vec![
+            SqlValue::Null,
+            SqlValue::Integer(0),
+            SqlValue::Integer(tile_column as i64),
+            SqlValue::Integer(tile_row as i64),
+            SqlValue::Blob(tile_data),
+        ]
+    });
+    db.write_table(
+        &table_name,
+        &format!(
+            "CREATE TABLE \"{}\" (id INTEGER PRIMARY KEY, zoom_level INTEGER NOT NULL, tile_column INTEGER NOT NULL, tile_row INTEGER NOT NULL, tile_data BLOB NOT NULL)",
+            table_name
+        ),
+        tile_rows,
+    )?;
+    if let Some(e) = error {
+        return Err(e);
+    }
+    db.finish()
+}
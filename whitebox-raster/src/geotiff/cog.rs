@@ -0,0 +1,512 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Writes Cloud-Optimized GeoTIFFs (COGs). A COG is a tiled GeoTIFF that contains a series
+of reduced-resolution overviews, with all of the IFDs placed at the start of the file, the
+full-resolution image first, followed by the tile data, smallest overview first. This layout
+allows a client to read the georeferencing, and any one overview, using a few HTTP range
+requests. The overviews are halved in size until they fit within a single tile. Continuous data
+are averaged, ignoring nodata cells, while categorical, Boolean, and RGB data take the value of
+the upper-left cell of each 2 x 2 block.
+
+Because the sizes of the compressed tiles are unknown until they have been written, the IFDs are
+first written with placeholder tile offsets and byte counts, and are then re-written in place
+once the tile data have been written. The tile offsets and byte counts occupy the same space in
+both passes.
+
+Compressed GeoTIFFs that are not COGs are written using the same layout, with smaller tiles and
+without overviews.
+*/
+
+use super::compression::{encode_block, predictor_for};
+use super::*;
+use std::io::Seek;
+
+/// The width and height of the tiles of a COG, the default of GDAL's COG driver.
+const COG_TILE_SIZE: usize = 512;
+
+/// The width and height of the tiles of other compressed GeoTIFFs, the default of GDAL.
+const TILE_SIZE: usize = 256;
+
+/// Writes a Cloud-Optimized GeoTIFF, optionally applying a predictor ahead of the compression.
+pub(super) fn write_cog(
+    r: &mut Raster,
+    compression: TiffCompression,
+    use_predictor: bool,
+) -> Result<(), Error> {
+    write_tiled_geotiff(r, compression, use_predictor, COG_TILE_SIZE, true)
+}
+
+/// Writes a tiled GeoTIFF without overviews, optionally applying a predictor ahead of the
+/// compression.
+pub(super) fn write_tiled(
+    r: &mut Raster,
+    compression: TiffCompression,
+    use_predictor: bool,
+) -> Result<(), Error> {
+    write_tiled_geotiff(r, compression, use_predictor, TILE_SIZE, false)
+}
+
+fn write_tiled_geotiff(
+    r: &mut Raster,
+    compression: TiffCompression,
+    use_predictor: bool,
+    tile_size: usize,
+    build_overviews: bool,
+) -> Result<(), Error> {
+    let resample_nearest = match r.configs.photometric_interp {
+        PhotometricInterpretation::Continuous => false,
+        PhotometricInterpretation::Categorical
+        | PhotometricInterpretation::Paletted
+        | PhotometricInterpretation::Boolean
+        | PhotometricInterpretation::RGB => true,
+        PhotometricInterpretation::Unknown => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Error while writing GeoTIFF file. Unknown Photometric Interpretation.",
+            ));
+        }
+    };
+
+    // As with write_geotiff, categorical and paletted output is written as continuous data.
+    if r.configs.photometric_interp == PhotometricInterpretation::Categorical
+        || r.configs.photometric_interp == PhotometricInterpretation::Paletted
+    {
+        r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+    }
+    let r: &Raster = r;
+
+    let is_rgb = r.configs.photometric_interp == PhotometricInterpretation::RGB;
+    let data_type = if is_rgb && r.configs.data_type == DataType::U32 {
+        DataType::RGBA32
+    } else {
+        r.configs.data_type
+    };
+    let (bits_per_sample, samples_per_pixel, sample_format) = match data_type {
+        DataType::U8 => (8u16, 1u16, 1u16),
+        DataType::I8 => (8u16, 1u16, 2u16),
+        DataType::U16 => (16u16, 1u16, 1u16),
+        DataType::I16 => (16u16, 1u16, 2u16),
+        DataType::U32 => (32u16, 1u16, 1u16),
+        DataType::I32 => (32u16, 1u16, 2u16),
+        DataType::F32 => (32u16, 1u16, 3u16),
+        DataType::U64 => (64u16, 1u16, 1u16),
+        DataType::I64 => (64u16, 1u16, 2u16),
+        DataType::F64 => (64u16, 1u16, 3u16),
+        DataType::RGB24 => (8u16, 3u16, 1u16),
+        DataType::RGBA32 => (8u16, 4u16, 1u16),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Unsupported data type for tiled GeoTIFF output: {:?}",
+                    r.configs.data_type
+                ),
+            ));
+        }
+    };
+    let bytes_per_pixel = data_type.get_data_size();
+    let predictor = if use_predictor && compression != TiffCompression::None {
+        predictor_for(sample_format)
+    } else {
+        PREDICTOR_NONE
+    };
+    let nodata = r.configs.nodata;
+    let endian = r.configs.endian;
+
+    //////////////////////////
+    // Create the overviews //
+    //////////////////////////
+    let mut overviews: Vec<(usize, usize, Vec<f64>)> = vec![];
+    let (mut rows, mut columns) = (r.configs.rows, r.configs.columns);
+    while build_overviews && (rows > tile_size || columns > tile_size) {
+        let (in_rows, in_columns) = (rows, columns);
+        rows = rows.div_ceil(2);
+        columns = columns.div_ceil(2);
+        let input: &[f64] = match overviews.last() {
+            Some(overview) => &overview.2,
+            None => &r.data,
+        };
+        let mut data = vec![nodata; rows * columns];
+        for row in 0..rows {
+            for col in 0..columns {
+                if resample_nearest {
+                    data[row * columns + col] = input[2 * row * in_columns + 2 * col];
+                    continue;
+                }
+                let mut sum = 0f64;
+                let mut n = 0f64;
+                for in_row in 2 * row..(2 * row + 2).min(in_rows) {
+                    for in_col in 2 * col..(2 * col + 2).min(in_columns) {
+                        let z = input[in_row * in_columns + in_col];
+                        if !is_nodata(z, nodata) {
+                            sum += z;
+                            n += 1f64;
+                        }
+                    }
+                }
+                if n > 0f64 {
+                    data[row * columns + col] = if data_type.is_integer() {
+                        (sum / n).round()
+                    } else {
+                        sum / n
+                    };
+                }
+            }
+        }
+        overviews.push((rows, columns, data));
+    }
+
+    // The full-resolution image, followed by the overviews in decreasing size
+    let mut images: Vec<(usize, usize, &[f64])> = vec![(r.configs.rows, r.configs.columns, &r.data)];
+    for (rows, columns, data) in &overviews {
+        images.push((*rows, *columns, data));
+    }
+    let num_tiles = |rows: usize, columns: usize| -> (usize, usize) {
+        (rows.div_ceil(tile_size), columns.div_ceil(tile_size))
+    };
+
+    // is it a BigTiff? Edge tiles are padded to the full tile size, and the compressed tiles are
+    // assumed to take their largest possible size, so that the tile offsets always fit. Each tile
+    // has a 4-byte offset and byte count in a classic TIFF.
+    let tile_bytes = tile_size * tile_size * bytes_per_pixel;
+    let predicted_size: u64 = 8u64
+        + images
+            .iter()
+            .map(|&(rows, columns, _)| {
+                let (down, across) = num_tiles(rows, columns);
+                (down * across) as u64 * (compression.max_encoded_size(tile_bytes) as u64 + 8u64)
+            })
+            .sum::<u64>();
+    let is_big_tiff = requires_big_tiff(predicted_size);
+    let header_size = if !is_big_tiff { 8u64 } else { 16u64 };
+
+    ////////////////////////////
+    // Create the IFD entries //
+    ////////////////////////////
+    let build_ifd = |level: usize,
+                     tile_offsets: &[u64],
+                     tile_byte_counts: &[u64]|
+     -> Result<(Vec<Entry>, Vec<u8>), Error> {
+        let (rows, columns, _) = images[level];
+        let mut ifd_entries: Vec<Entry> = vec![];
+        let mut larger_values_data = ByteOrderWriter::<Vec<u8>>::new(vec![], endian);
+
+        // NewSubfileType tag (254); the overviews are reduced-resolution versions of the image
+        if level > 0 {
+            ifd_entries.push(Entry::new(TAG_NEWSUBFILETYPE, DT_LONG, 1u64, 1u64));
+        }
+
+        ifd_entries.push(Entry::new(TAG_IMAGEWIDTH, DT_LONG, 1u64, columns as u64));
+        ifd_entries.push(Entry::new(TAG_IMAGELENGTH, DT_LONG, 1u64, rows as u64));
+
+        if samples_per_pixel == 1 {
+            ifd_entries.push(Entry::new(
+                TAG_BITSPERSAMPLE,
+                DT_SHORT,
+                1u64,
+                bits_per_sample as u64,
+            ));
+        } else {
+            ifd_entries.push(Entry::new(
+                TAG_BITSPERSAMPLE,
+                DT_SHORT,
+                samples_per_pixel as u64,
+                larger_values_data.len() as u64,
+            ));
+            for _ in 0..samples_per_pixel {
+                larger_values_data.write_u16(bits_per_sample)?;
+            }
+        }
+
+        ifd_entries.push(Entry::new(
+            TAG_COMPRESSION,
+            DT_SHORT,
+            1u64,
+            compression.tag_value() as u64,
+        ));
+
+        let pi = if is_rgb { PI_RGB } else { PI_BLACKISZERO };
+        ifd_entries.push(Entry::new(
+            TAG_PHOTOMETRICINTERPRETATION,
+            DT_SHORT,
+            1u64,
+            pi as u64,
+        ));
+
+        ifd_entries.push(Entry::new(
+            TAG_SAMPLESPERPIXEL,
+            DT_SHORT,
+            1u64,
+            samples_per_pixel as u64,
+        ));
+        ifd_entries.push(Entry::new(TAG_PLANARCONFIGURATION, DT_SHORT, 1u64, 1u64));
+
+        if predictor != PREDICTOR_NONE {
+            ifd_entries.push(Entry::new(TAG_PREDICTOR, DT_SHORT, 1u64, predictor as u64));
+        }
+
+        ifd_entries.push(Entry::new(TAG_TILEWIDTH, DT_SHORT, 1u64, tile_size as u64));
+        ifd_entries.push(Entry::new(TAG_TILELENGTH, DT_SHORT, 1u64, tile_size as u64));
+        push_tile_entry(
+            TAG_TILEOFFSETS,
+            tile_offsets,
+            is_big_tiff,
+            &mut ifd_entries,
+            &mut larger_values_data,
+        )?;
+        push_tile_entry(
+            TAG_TILEBYTECOUNTS,
+            tile_byte_counts,
+            is_big_tiff,
+            &mut ifd_entries,
+            &mut larger_values_data,
+        )?;
+
+        if level == 0 {
+            push_descriptive_entries(
+                r,
+                is_big_tiff,
+                samples_per_pixel,
+                &mut ifd_entries,
+                &mut larger_values_data,
+            )?;
+        } else {
+            // The overviews share the georeferencing of the full-resolution image
+            if samples_per_pixel == 4 {
+                ifd_entries.push(Entry::new(TAG_EXTRASAMPLES, DT_SHORT, 1u64, 2u64));
+            }
+            if samples_per_pixel == 1 {
+                ifd_entries.push(Entry::new(
+                    TAG_SAMPLEFORMAT,
+                    DT_SHORT,
+                    1u64,
+                    sample_format as u64,
+                ));
+            } else {
+                ifd_entries.push(Entry::new(
+                    TAG_SAMPLEFORMAT,
+                    DT_SHORT,
+                    samples_per_pixel as u64,
+                    larger_values_data.len() as u64,
+                ));
+                for _ in 0..samples_per_pixel {
+                    larger_values_data.write_u16(sample_format)?;
+                }
+            }
+        }
+
+        Ok((ifd_entries, larger_values_data.get_inner().clone()))
+    };
+
+    // Lay out the IFDs, each followed by its larger values, ahead of the image data.
+    let mut ifds = vec![];
+    let mut ifd_starts = vec![];
+    let mut current_offset = header_size;
+    for (level, &(rows, columns, _)) in images.iter().enumerate() {
+        let (down, across) = num_tiles(rows, columns);
+        let placeholders = vec![0u64; down * across];
+        let (ifd_entries, larger_values_data) = build_ifd(level, &placeholders, &placeholders)?;
+        ifd_starts.push(current_offset);
+        current_offset += if !is_big_tiff {
+            2u64 + ifd_entries.len() as u64 * 12u64 + 4u64
+        } else {
+            8u64 + ifd_entries.len() as u64 * 20u64 + 8u64
+        };
+        current_offset += larger_values_data.len() as u64;
+        // The next IFD, or the image data, must start on a word.
+        current_offset += current_offset % 2;
+        ifds.push((ifd_entries, larger_values_data));
+    }
+
+    //////////////////////
+    // Write the header //
+    //////////////////////
+    let f = File::create(r.file_name.clone())?;
+    let mut writer = BufWriter::new(f);
+    if endian == Endianness::LittleEndian {
+        write_bytes(&mut writer, "II".as_bytes())?;
+    } else {
+        write_bytes(&mut writer, "MM".as_bytes())?;
+    }
+    if !is_big_tiff {
+        write_u16(&mut writer, endian, 42u16)?;
+        write_u32(&mut writer, endian, header_size as u32)?;
+    } else {
+        write_u16(&mut writer, endian, 43u16)?;
+        write_u16(&mut writer, endian, 8u16)?;
+        write_u16(&mut writer, endian, 0u16)?;
+        write_u64(&mut writer, endian, header_size)?;
+    }
+
+    let num_images = ifds.len();
+    for (level, (ifd_entries, larger_values_data)) in ifds.into_iter().enumerate() {
+        let next_ifd = if level + 1 < num_images {
+            ifd_starts[level + 1]
+        } else {
+            0u64
+        };
+        write_ifd(
+            &mut writer,
+            endian,
+            is_big_tiff,
+            ifd_starts[level],
+            ifd_entries,
+            &larger_values_data,
+            next_ifd,
+        )?;
+        if larger_values_data.len() % 2 == 1 {
+            write_u8(&mut writer, 0u8)?;
+        }
+    }
+
+    //////////////////////////
+    // Write the image data //
+    //////////////////////////
+    let mut tile_offsets = vec![vec![]; num_images];
+    let mut tile_byte_counts = vec![vec![]; num_images];
+    for level in (0..num_images).rev() {
+        let (rows, columns, data) = images[level];
+        let (down, across) = num_tiles(rows, columns);
+        for tile_row in 0..down {
+            for tile_col in 0..across {
+                let mut tile = Vec::with_capacity(tile_size * tile_size * bytes_per_pixel);
+                for row in tile_row * tile_size..(tile_row + 1) * tile_size {
+                    for col in tile_col * tile_size..(tile_col + 1) * tile_size {
+                        let z = if row < rows && col < columns {
+                            data[row * columns + col]
+                        } else {
+                            nodata
+                        };
+                        encode_value(&mut tile, z, data_type, endian);
+                    }
+                }
+                let tile = encode_block(
+                    tile,
+                    compression,
+                    predictor,
+                    tile_size,
+                    samples_per_pixel as usize,
+                    bytes_per_pixel / samples_per_pixel as usize,
+                    endian,
+                )?;
+                write_bytes(&mut writer, &tile)?;
+                tile_offsets[level].push(current_offset);
+                tile_byte_counts[level].push(tile.len() as u64);
+                current_offset += tile.len() as u64;
+                if tile.len() % 2 != 0 {
+                    // This is just because the data must start on a word (i.e. an even value).
+                    write_u8(&mut writer, 0u8)?;
+                    current_offset += 1;
+                }
+            }
+        }
+    }
+
+    // Now that the tile offsets and byte counts are known, re-write the IFDs.
+    for level in 0..num_images {
+        let (ifd_entries, larger_values_data) =
+            build_ifd(level, &tile_offsets[level], &tile_byte_counts[level])?;
+        let next_ifd = if level + 1 < num_images {
+            ifd_starts[level + 1]
+        } else {
+            0u64
+        };
+        writer.seek(SeekFrom::Start(ifd_starts[level]))?;
+        write_ifd(
+            &mut writer,
+            endian,
+            is_big_tiff,
+            ifd_starts[level],
+            ifd_entries,
+            &larger_values_data,
+            next_ifd,
+        )?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Adds a TileOffsets or TileByteCounts entry, which holds one value per tile.
+fn push_tile_entry(
+    tag: u16,
+    values: &[u64],
+    is_big_tiff: bool,
+    ifd_entries: &mut Vec<Entry>,
+    larger_values_data: &mut ByteOrderWriter<Vec<u8>>,
+) -> Result<(), Error> {
+    let ifd_type = if !is_big_tiff { DT_LONG } else { DT_TIFF_LONG8 };
+    if values.len() == 1 {
+        if !is_big_tiff && values[0] > CLASSIC_TIFF_MAX_OFFSET {
+            return Err(classic_tiff_overflow());
+        }
+        // a single value is stored within the entry
+        ifd_entries.push(Entry::new(tag, ifd_type, 1u64, values[0]));
+        return Ok(());
+    }
+    ifd_entries.push(Entry::new(
+        tag,
+        ifd_type,
+        values.len() as u64,
+        larger_values_data.len() as u64,
+    ));
+    for &val in values {
+        if !is_big_tiff {
+            if val > CLASSIC_TIFF_MAX_OFFSET {
+                return Err(classic_tiff_overflow());
+            }
+            larger_values_data.write_u32(val as u32)?;
+        } else {
+            larger_values_data.write_u64(val)?;
+        }
+    }
+    Ok(())
+}
+
+/// Appends the bytes of a cell value, of the given data type, to a tile.
+fn encode_value(tile: &mut Vec<u8>, value: f64, data_type: DataType, endian: Endianness) {
+    macro_rules! push_bytes {
+        ($v:expr) => {
+            if endian == Endianness::LittleEndian {
+                tile.extend_from_slice(&$v.to_le_bytes())
+            } else {
+                tile.extend_from_slice(&$v.to_be_bytes())
+            }
+        };
+    }
+    match data_type {
+        DataType::F64 => push_bytes!(value),
+        DataType::F32 => push_bytes!(value as f32),
+        DataType::U64 => push_bytes!(value as u64),
+        DataType::I64 => push_bytes!(value as i64),
+        DataType::U32 => push_bytes!(value as u32),
+        DataType::I32 => push_bytes!(value as i32),
+        DataType::U16 => push_bytes!(value as u16),
+        DataType::I16 => push_bytes!(value as i16),
+        DataType::U8 => tile.push(value as u8),
+        DataType::I8 => tile.push(value as i8 as u8),
+        DataType::RGB24 => {
+            let val = value as u32;
+            tile.extend_from_slice(&[
+                (val & 0xFF) as u8,            // red
+                ((val >> 8u32) & 0xFF) as u8,  // green
+                ((val >> 16u32) & 0xFF) as u8, // blue
+            ]);
+        }
+        DataType::RGBA32 => {
+            let val = value as u32;
+            tile.extend_from_slice(&[
+                (val & 0xFF) as u8,            // red
+                ((val >> 8u32) & 0xFF) as u8,  // green
+                ((val >> 16u32) & 0xFF) as u8, // blue
+                ((val >> 24u32) & 0xFF) as u8, // a
+            ]);
+        }
+        _ => {}
+    }
+}
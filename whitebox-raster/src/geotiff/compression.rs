@@ -0,0 +1,246 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: The compression of the tiles of GeoTIFF output, and the horizontal predictors that may be
+applied ahead of it. A predictor replaces each sample of a row with its difference from the
+preceding sample, which greatly improves the compression of smoothly varying data, such as
+elevations. Integer data use the horizontal differencing predictor (Predictor=2) and
+floating-point data use the floating-point predictor (Predictor=3), which splits the samples of
+each row into byte planes, most-significant byte first, before differencing the bytes. Both are
+applied to each row of a tile separately, as libtiff does.
+*/
+
+use super::tiff_consts::*;
+use crate::zstd_codec;
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use std::io::{Error, ErrorKind};
+use whitebox_common::configs::Configs;
+use whitebox_common::utils::{lzw_compress, Endianness};
+use whitebox_common::errors::ErrorCause;
+
+/// The ZSTD compression level used for GeoTIFF output, which favours smaller files over speed.
+const ZSTD_LEVEL: i32 = 9;
+
+/// The compression method of GeoTIFF output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum TiffCompression {
+    None,
+    Lzw,
+    Deflate,
+    Zstd,
+}
+
+impl TiffCompression {
+    /// Returns the compression named by the geotiff_compression setting, or no compression when
+    /// the compress_rasters setting is false.
+    pub(crate) fn from_configs(configs: &Configs) -> Result<TiffCompression, Error> {
+        if !configs.compress_rasters {
+            return Ok(TiffCompression::None);
+        }
+        match configs.geotiff_compression.trim().to_lowercase().as_str() {
+            "none" => Ok(TiffCompression::None),
+            "lzw" => Ok(TiffCompression::Lzw),
+            "" | "deflate" => Ok(TiffCompression::Deflate),
+            "zstd" => Ok(TiffCompression::Zstd),
+            s => Err(ErrorCause::UnsupportedFormat.error(
+                format!(
+                    "Unsupported GeoTIFF compression '{}'; use 'none', 'lzw', 'deflate', or 'zstd'.",
+                    s
+                ),
+            )),
+        }
+    }
+
+    /// Returns the largest size that a block of `len` bytes may take once compressed, i.e. when
+    /// the data cannot be compressed. LZW may expand such data by up to half, since each byte may
+    /// take a 12-bit code.
+    pub(crate) fn max_encoded_size(&self, len: usize) -> usize {
+        match self {
+            TiffCompression::None => len,
+            TiffCompression::Lzw => len + len / 2 + 16,
+            // stored blocks of up to 65,535 bytes, each with a 5-byte header, and the zlib wrapper
+            TiffCompression::Deflate => len + 5 * (len / 65_535 + 1) + 6,
+            TiffCompression::Zstd => zstd_codec::compress_bound(len),
+        }
+    }
+
+    /// The value of the Compression tag (259).
+    pub(crate) fn tag_value(&self) -> u16 {
+        match self {
+            TiffCompression::None => COMPRESS_NONE,
+            TiffCompression::Lzw => COMPRESS_LZW,
+            TiffCompression::Deflate => COMPRESS_DEFLATE,
+            TiffCompression::Zstd => COMPRESS_ZSTD,
+        }
+    }
+}
+
+/// Returns the value of the Predictor tag (317) suited to samples of the given format, where
+/// a sample format of 3 indicates floating-point data.
+pub(crate) fn predictor_for(sample_format: u16) -> u16 {
+    if sample_format == 3 {
+        PREDICTOR_FLOATINGPOINT
+    } else {
+        PREDICTOR_HORIZONTAL
+    }
+}
+
+/// Applies a predictor to the rows of a block of samples, each `row_width` pixels wide, and then
+/// compresses the block.
+pub(crate) fn encode_block(
+    mut block: Vec<u8>,
+    compression: TiffCompression,
+    predictor: u16,
+    row_width: usize,
+    samples_per_pixel: usize,
+    bytes_per_sample: usize,
+    endian: Endianness,
+) -> Result<Vec<u8>, Error> {
+    if compression == TiffCompression::None {
+        return Ok(block);
+    }
+    let row_length = row_width * samples_per_pixel * bytes_per_sample;
+    for row in block.chunks_exact_mut(row_length) {
+        match predictor {
+            PREDICTOR_HORIZONTAL => {
+                horizontal_difference(row, samples_per_pixel, bytes_per_sample, endian)
+            }
+            PREDICTOR_FLOATINGPOINT => {
+                floating_point_difference(row, samples_per_pixel, bytes_per_sample, endian)
+            }
+            _ => {}
+        }
+    }
+    match compression {
+        TiffCompression::Lzw => Ok(lzw_compress(&block)),
+        TiffCompression::Deflate => Ok(compress_to_vec_zlib(&block, 6)),
+        TiffCompression::Zstd => zstd_codec::compress(&block, ZSTD_LEVEL),
+        TiffCompression::None => Ok(block),
+    }
+}
+
+/// Reverses the predictor applied to the rows of a decompressed block, each `row_width` pixels
+/// wide.
+pub(crate) fn undo_predictor(
+    block: &mut [u8],
+    predictor: u16,
+    row_width: usize,
+    samples_per_pixel: usize,
+    bytes_per_sample: usize,
+    endian: Endianness,
+) -> Result<(), Error> {
+    let row_length = row_width * samples_per_pixel * bytes_per_sample;
+    if predictor == PREDICTOR_NONE || row_length == 0 {
+        return Ok(());
+    }
+    for row in block.chunks_exact_mut(row_length) {
+        match predictor {
+            PREDICTOR_HORIZONTAL => {
+                horizontal_accumulate(row, samples_per_pixel, bytes_per_sample, endian)
+            }
+            PREDICTOR_FLOATINGPOINT => {
+                floating_point_accumulate(row, samples_per_pixel, bytes_per_sample, endian)
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Unsupported GeoTIFF predictor ({}).", predictor),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decompresses a ZSTD-compressed block.
+pub(crate) fn zstd_decompress(compressed: &[u8]) -> Result<Vec<u8>, Error> {
+    zstd_codec::decode_all(compressed)
+}
+
+fn read_sample(bytes: &[u8], endian: Endianness) -> u64 {
+    let mut value = 0u64;
+    if endian == Endianness::LittleEndian {
+        for &b in bytes.iter().rev() {
+            value = (value << 8) | b as u64;
+        }
+    } else {
+        for &b in bytes {
+            value = (value << 8) | b as u64;
+        }
+    }
+    value
+}
+
+fn write_sample(bytes: &mut [u8], value: u64, endian: Endianness) {
+    let n = bytes.len();
+    for (k, b) in bytes.iter_mut().enumerate() {
+        let shift = if endian == Endianness::LittleEndian {
+            8 * k
+        } else {
+            8 * (n - 1 - k)
+        };
+        *b = (value >> shift) as u8;
+    }
+}
+
+fn horizontal_difference(row: &mut [u8], spp: usize, bps: usize, endian: Endianness) {
+    let num_samples = row.len() / bps;
+    for i in (spp..num_samples).rev() {
+        let prev = read_sample(&row[(i - spp) * bps..(i - spp + 1) * bps], endian);
+        let sample = &mut row[i * bps..(i + 1) * bps];
+        let value = read_sample(sample, endian).wrapping_sub(prev);
+        write_sample(sample, value, endian);
+    }
+}
+
+fn horizontal_accumulate(row: &mut [u8], spp: usize, bps: usize, endian: Endianness) {
+    let num_samples = row.len() / bps;
+    for i in spp..num_samples {
+        let prev = read_sample(&row[(i - spp) * bps..(i - spp + 1) * bps], endian);
+        let sample = &mut row[i * bps..(i + 1) * bps];
+        let value = read_sample(sample, endian).wrapping_add(prev);
+        write_sample(sample, value, endian);
+    }
+}
+
+/// Returns the position, within the byte planes of a row of `num_samples` samples, of byte `b`
+/// of sample `i`, as stored in a file with the given byte order.
+fn plane_index(i: usize, b: usize, bps: usize, num_samples: usize, endian: Endianness) -> usize {
+    let plane = if endian == Endianness::LittleEndian {
+        bps - 1 - b
+    } else {
+        b
+    };
+    plane * num_samples + i
+}
+
+fn floating_point_difference(row: &mut [u8], spp: usize, bps: usize, endian: Endianness) {
+    let num_samples = row.len() / bps;
+    let mut planes = vec![0u8; row.len()];
+    for i in 0..num_samples {
+        for b in 0..bps {
+            planes[plane_index(i, b, bps, num_samples, endian)] = row[i * bps + b];
+        }
+    }
+    for k in (spp..planes.len()).rev() {
+        planes[k] = planes[k].wrapping_sub(planes[k - spp]);
+    }
+    row.copy_from_slice(&planes);
+}
+
+fn floating_point_accumulate(row: &mut [u8], spp: usize, bps: usize, endian: Endianness) {
+    let num_samples = row.len() / bps;
+    for k in spp..row.len() {
+        row[k] = row[k].wrapping_add(row[k - spp]);
+    }
+    let planes = row.to_vec();
+    for i in 0..num_samples {
+        for b in 0..bps {
+            row[i * bps + b] = planes[plane_index(i, b, bps, num_samples, endian)];
+        }
+    }
+}
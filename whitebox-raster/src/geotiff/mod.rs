@@ -1,4 +1,6 @@
 #![allow(unused_assignments, dead_code)]
+mod cog;
+pub(crate) mod compression;
 pub mod geokeys;
 pub mod ifd;
 pub mod tiff_consts;
@@ -10,8 +12,7 @@ use crate::geotiff::tiff_consts::*;
 use crate::*;
 use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
 use whitebox_common::structures::{Point2D, PolynomialRegression2D};
-use whitebox_common::utils::{ByteOrderReader, ByteOrderWriter, Endianness};
-use miniz_oxide::deflate::compress_to_vec_zlib;
+use whitebox_common::utils::{open_input, ByteOrderReader, ByteOrderWriter, Endianness, ReadSeek};
 use miniz_oxide::inflate::decompress_to_vec_zlib;
 // use libflate::zlib::Decoder;
 // use libflate::deflate::Encoder;
@@ -21,6 +22,7 @@ use std::collections::HashMap;
 use std::default::Default;
 use std::f64;
 // use std::fs;
+use compression::TiffCompression;
 use ifd::{Entry, Ifd};
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Cursor, Error, ErrorKind, SeekFrom};
@@ -28,6 +30,28 @@ use std::io::{BufReader, BufWriter, Cursor, Error, ErrorKind, SeekFrom};
 use std::io::Write;
 use std::mem;
 
+/// The largest offset that can be stored in a classic TIFF, which uses 32-bit offsets.
+const CLASSIC_TIFF_MAX_OFFSET: u64 = u32::MAX as u64;
+
+/// Room allowed for the IFD entries and tag values, e.g. the georeferencing, that are not
+/// counted in the predicted size of a GeoTIFF.
+const TAG_DATA_ALLOWANCE: u64 = 16 * 1_048_576;
+
+/// Returns true if a GeoTIFF of `predicted_size` bytes, counting its image data and its block
+/// offsets and byte counts, must be written as a BigTIFF, which uses 64-bit offsets.
+pub(crate) fn requires_big_tiff(predicted_size: u64) -> bool {
+    predicted_size + TAG_DATA_ALLOWANCE > CLASSIC_TIFF_MAX_OFFSET
+}
+
+/// The error returned when a classic TIFF would need an offset beyond the 4 GB limit, which
+/// would otherwise be silently truncated.
+fn classic_tiff_overflow() -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        "The GeoTIFF exceeds the 4 GB limit of a classic TIFF but was not written as a BigTIFF.",
+    )
+}
+
 pub fn print_tags<'a>(file_name: &'a String) -> Result<(), Error> {
     let f = File::open(file_name.clone())?;
 
@@ -228,18 +252,275 @@ pub fn print_tags<'a>(file_name: &'a String) -> Result<(), Error> {
     Ok(())
 }
 
+/// The physical layout of a TIFF file, i.e. properties of the file that are not otherwise
+/// captured in the raster configurations.
+#[derive(Default, Clone, Debug)]
+pub struct TiffLayout {
+    pub is_big_tiff: bool,
+    pub endian: Endianness,
+    pub compression: u16,
+    pub predictor: u16,
+    pub planar_configuration: u16,
+    pub samples_per_pixel: u16,
+    pub bits_per_sample: Vec<u16>,
+    pub sample_format: u16,
+    pub is_tiled: bool,
+    pub tile_width: u64,
+    pub tile_length: u64,
+    pub rows_per_strip: u64,
+    /// The number of tiles, or of strips for strip-organized files.
+    pub num_blocks: u64,
+    /// The number of images in the file, including any overviews.
+    pub num_images: usize,
+    /// The index, number of columns and number of rows of each of the reduced-resolution
+    /// images (overviews) in the file, excluding transparency masks.
+    pub overviews: Vec<(usize, usize, usize)>,
+}
+
+impl TiffLayout {
+    /// Returns the name of the compression method.
+    pub fn compression_name(&self) -> String {
+        match self.compression {
+            1 => "None",
+            2 => "CCITT RLE",
+            5 => "LZW",
+            6 | 7 => "JPEG",
+            8 | 32946 => "Deflate",
+            32773 => "PackBits",
+            34887 => "LERC",
+            50000 => "ZSTD",
+            _ => "Unknown",
+        }
+        .to_string()
+    }
+}
+
+/// Reads the layout (compression, tiling, etc.) of a TIFF file from its first image file
+/// directory, without reading the image data.
+pub fn read_tiff_layout<'a>(file_name: &'a str) -> Result<TiffLayout, Error> {
+    let f = open_input(file_name)?;
+    let br = BufReader::new(f);
+    let mut th = ByteOrderReader::<BufReader<Box<dyn ReadSeek>>>::new(br, Endianness::LittleEndian);
+
+    let bo_indicator1 = th.read_u8()?;
+    let bo_indicator2 = th.read_u8()?;
+    let endian = if bo_indicator1 == 73 && bo_indicator2 == 73 {
+        Endianness::LittleEndian
+    } else if bo_indicator1 == 77 && bo_indicator2 == 77 {
+        Endianness::BigEndian
+    } else {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "Incorrect TIFF header. Unrecognized byte-order indicator.",
+        ));
+    };
+    if th.get_byte_order() != endian {
+        th.set_byte_order(endian);
+    }
+
+    let is_big_tiff = match th.read_u16()? {
+        42 => false,
+        43 => true,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Incorrect TIFF header. Unrecognized magic number.",
+            ))
+        }
+    };
+
+    let mut ifd_offset = if !is_big_tiff {
+        th.read_u32()? as usize
+    } else {
+        th.read_u16()?; // bytesize of offsets
+        th.read_u16()?;
+        th.read_u64()? as usize
+    };
+
+    // Only the values of the tags in the first IFD are needed, other than the dimensions and
+    // subfile types of the later images, which identify the overviews.
+    let mut first_ifd: HashMap<u16, Ifd> = HashMap::new();
+    let mut num_images = 0usize;
+    let mut num_blocks = 0u64;
+    let mut overviews = vec![];
+    while ifd_offset > 0 {
+        let mut image_ifd: HashMap<u16, Ifd> = HashMap::new();
+        th.seek(ifd_offset);
+        let num_directories = if !is_big_tiff {
+            th.read_u16()? as u64
+        } else {
+            th.read_u64()?
+        };
+        for _ in 0..num_directories {
+            let tag_id = th.read_u16()?;
+            let field_type = th.read_u16()?;
+            let num_values = if !is_big_tiff {
+                th.read_u32()? as u64
+            } else {
+                th.read_u64()?
+            };
+            let value_offset = if !is_big_tiff {
+                th.read_u32()? as u64
+            } else {
+                th.read_u64()?
+            };
+            if num_images > 0 && tag_id != 254 && tag_id != 256 && tag_id != 257 {
+                continue;
+            }
+            if tag_id == TAG_TILEOFFSETS || tag_id == TAG_STRIPOFFSETS {
+                num_blocks = num_values;
+            }
+            let data_size = match field_type {
+                1u16 | 2u16 | 6u16 | 7u16 => 1u64,
+                3u16 | 8u16 => 2u64,
+                4u16 | 9u16 | 11u16 => 4u64,
+                5u16 | 10u16 | 12u16 => 8u64,
+                16u16 | 17u16 | 18u16 => 8u64,
+                _ => continue,
+            };
+            // only the first value of each tag is needed, with the exception of BitsPerSample
+            let n = if tag_id == TAG_BITSPERSAMPLE { num_values } else { 1 };
+            let offset_size = if is_big_tiff { 8 } else { 4 };
+            let cur_pos = th.pos();
+            if data_size * num_values > offset_size {
+                th.seek(value_offset as usize);
+            } else {
+                th.seek(cur_pos - offset_size as usize);
+            }
+            let mut data: Vec<u8> = vec![];
+            for _ in 0..n * data_size {
+                data.push(th.read_u8()?);
+            }
+            th.seek(cur_pos);
+            image_ifd.insert(tag_id, Ifd::new(tag_id, field_type, n, value_offset, data, endian));
+        }
+        if num_images == 0 {
+            first_ifd = image_ifd;
+        } else {
+            // NewSubfileType bit 0 marks a reduced-resolution image and bit 2 a transparency mask.
+            let subfile_type = image_ifd.get(&254).map_or(0, |ifd| ifd_first_value(ifd, 0));
+            if subfile_type & 1 == 1 && subfile_type & 4 == 0 {
+                let columns = image_ifd.get(&256).map_or(0, |ifd| ifd_first_value(ifd, 0));
+                let rows = image_ifd.get(&257).map_or(0, |ifd| ifd_first_value(ifd, 0));
+                if columns > 0 && rows > 0 {
+                    overviews.push((num_images, columns as usize, rows as usize));
+                }
+            }
+        }
+        num_images += 1;
+        ifd_offset = if !is_big_tiff {
+            th.read_u32()? as usize
+        } else {
+            th.read_u64()? as usize
+        };
+    }
+
+    let first_value = |tag: u16, default: u64| -> u64 {
+        match first_ifd.get(&tag) {
+            Some(ifd) => ifd_first_value(ifd, default),
+            None => default,
+        }
+    };
+
+    let is_tiled = first_ifd.contains_key(&TAG_TILEWIDTH);
+    let bits_per_sample = match first_ifd.get(&TAG_BITSPERSAMPLE) {
+        Some(ifd) => ifd.interpret_as_u16(),
+        None => vec![1u16],
+    };
+
+    Ok(TiffLayout {
+        is_big_tiff: is_big_tiff,
+        endian: endian,
+        compression: first_value(TAG_COMPRESSION, 1) as u16,
+        predictor: first_value(TAG_PREDICTOR, 1) as u16,
+        planar_configuration: first_value(TAG_PLANARCONFIGURATION, 1) as u16,
+        samples_per_pixel: first_value(TAG_SAMPLESPERPIXEL, 1) as u16,
+        bits_per_sample: bits_per_sample,
+        sample_format: first_value(TAG_SAMPLEFORMAT, 1) as u16,
+        is_tiled: is_tiled,
+        tile_width: first_value(TAG_TILEWIDTH, 0),
+        tile_length: first_value(TAG_TILELENGTH, 0),
+        rows_per_strip: first_value(TAG_ROWSPERSTRIP, 0),
+        num_blocks: num_blocks,
+        num_images: num_images,
+        overviews: overviews,
+    })
+}
+
+/// Returns the first value of an integer-valued tag, or `default` for other types of tags.
+fn ifd_first_value(ifd: &Ifd, default: u64) -> u64 {
+    match ifd.ifd_type {
+        3 => ifd.interpret_as_u16()[0] as u64,
+        4 => ifd.interpret_as_u32()[0] as u64,
+        16 => ifd.interpret_as_u64()[0],
+        _ => default,
+    }
+}
+
+/// The placement and encoding of the cells of an uncompressed, single-band GeoTIFF. This is
+/// enough to read cell values directly out of the file, e.g. through a memory map, without
+/// decoding the whole image.
+#[derive(Clone, Debug)]
+pub(crate) struct GeoTiffBlockLayout {
+    pub block_width: usize,
+    pub block_height: usize,
+    pub blocks_across: usize,
+    pub block_offsets: Vec<u64>,
+    pub block_counts: Vec<u64>,
+    pub bits_per_sample: u16,
+    pub sample_format: u16,
+}
+
 pub fn read_geotiff<'a>(
     file_name: &'a String,
     configs: &'a mut RasterConfigs,
     data: &'a mut Vec<f64>,
 ) -> Result<(), Error> {
-    let f = File::open(file_name.clone())?;
+    read_geotiff_impl(file_name, configs, Some(data), 0).map(|_| ())
+}
+
+/// Reads one of the reduced-resolution images (overviews) of a GeoTIFF, given its index among
+/// the images in the file (see `TiffLayout::overviews`). The georeferencing of the overview is
+/// derived from that of the full-resolution image, scaled to the overview's dimensions.
+pub fn read_geotiff_overview<'a>(
+    file_name: &'a String,
+    configs: &'a mut RasterConfigs,
+    data: &'a mut Vec<f64>,
+    image: usize,
+) -> Result<(), Error> {
+    read_geotiff_impl(file_name, configs, Some(data), image).map(|_| ())
+}
+
+/// Reads the header of a GeoTIFF into `configs` and returns the layout of its cells,
+/// without reading the data. Only uncompressed, single-band, greyscale images without
+/// a predictor can be described this way; anything else returns an `InvalidInput` error.
+pub(crate) fn read_geotiff_block_layout(
+    file_name: &str,
+    configs: &mut RasterConfigs,
+) -> Result<GeoTiffBlockLayout, Error> {
+    match read_geotiff_impl(&file_name.to_string(), configs, None, 0)? {
+        Some(layout) => Ok(layout),
+        None => Err(Error::new(
+            ErrorKind::InvalidData,
+            "The GeoTIFF block layout was not read correctly.",
+        )),
+    }
+}
+
+fn read_geotiff_impl<'a>(
+    file_name: &'a String,
+    configs: &'a mut RasterConfigs,
+    data: Option<&'a mut Vec<f64>>,
+    image: usize,
+) -> Result<Option<GeoTiffBlockLayout>, Error> {
+    // GeoTIFFs in object stores are read with range requests, e.g. cloud-optimized GeoTIFFs
+    let f = open_input(file_name)?;
 
     //////////////////////////
     // Read the TIFF header //
     //////////////////////////
     let br = BufReader::new(f);
-    let mut th = ByteOrderReader::<BufReader<File>>::new(br, configs.endian);
+    let mut th = ByteOrderReader::<BufReader<Box<dyn ReadSeek>>>::new(br, configs.endian);
 
     let bo_indicator1 = th.read_u8()?;
     let bo_indicator2 = th.read_u8()?;
@@ -301,6 +582,8 @@ pub fn read_geotiff<'a>(
     //////////////////
 
     let mut ifd_map = HashMap::new();
+    let mut first_ifd_map = HashMap::new();
+    let mut image_num = 0usize;
     let mut geokeys: GeoKeys = Default::default();
     let mut cur_pos: usize;
     while ifd_offset > 0 {
@@ -393,20 +676,56 @@ pub fn read_geotiff<'a>(
             ifd_map.insert(tag_id, ifd.clone());
         }
         // WhiteboxTools currently only supports single-band rasters.
-        // Sometimes GeoTIFF contain multiple bands. When this is the case,
-        // only the first band should be read. This is often the case when
-        // users have used pyramiding on their file. To get the tags of the
-        // other bands, uncomment the code below; however, doing so may
-        // cause erratic behaviour of certain tools. e.g. see issue # 102
+        // Sometimes GeoTIFF contain multiple images, most often because
+        // users have used pyramiding on their file. Only the requested
+        // image, which is the first one unless an overview is being read,
+        // is decoded; reading the others as bands may cause erratic
+        // behaviour of certain tools, e.g. see issue # 102
         // clip_raster_to_polygon issue
+        if image_num == image {
+            break;
+        }
+        if image_num == 0 {
+            first_ifd_map = ifd_map;
+            ifd_map = HashMap::new();
+        } else {
+            ifd_map.clear();
+        }
+        image_num += 1;
+        ifd_offset = if !is_big_tiff {
+            th.read_u32()? as usize
+        } else {
+            th.read_u64()? as usize
+        };
+    }
 
-        // if !is_big_tiff {
-        //     ifd_offset = th.read_u32()? as usize;
-        // } else {
-        //     ifd_offset = th.read_u64()? as usize;
-        // }
-
-        ifd_offset = 0; // comment this out if you want to read additional images.
+    // Overviews are georeferenced by the tags of the full-resolution image, which apply to the
+    // overview after scaling its pixel coordinates by the ratio of the images' dimensions.
+    let mut overview_scale = (1f64, 1f64);
+    if image > 0 {
+        if image_num != image || ifd_map.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The TIFF file {} does not contain an image with index {}.", file_name, image),
+            ));
+        }
+        for tag in [33550u16, 33920, 33922, 34264, 34735, 34736, 34737, 42112, 42113].iter() {
+            if !ifd_map.contains_key(tag) {
+                if let Some(ifd) = first_ifd_map.get(tag) {
+                    ifd_map.insert(*tag, ifd.clone());
+                }
+            }
+        }
+        let dimension = |map: &HashMap<u16, Ifd>, tag: u16| -> f64 {
+            map.get(&tag).map_or(0f64, |ifd| ifd_first_value(ifd, 0) as f64)
+        };
+        let (overview_columns, overview_rows) = (dimension(&ifd_map, 256), dimension(&ifd_map, 257));
+        if overview_columns > 0f64 && overview_rows > 0f64 {
+            overview_scale = (
+                dimension(&first_ifd_map, 256) / overview_columns,
+                dimension(&first_ifd_map, 257) / overview_rows,
+            );
+        }
     }
 
     configs.columns = match ifd_map.get(&256) {
@@ -467,14 +786,20 @@ pub fn read_geotiff<'a>(
         && compression != COMPRESS_PACKBITS
         && compression != COMPRESS_LZW
         && compression != COMPRESS_DEFLATE
+        && compression != COMPRESS_ZSTD
     {
         println!("Compression: {}", compression);
         return Err(Error::new(
             ErrorKind::InvalidData,
-            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS, LZW, and DEFLATE compression.",
+            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS, LZW, DEFLATE, and ZSTD compression.",
         ));
     }
 
+    let predictor = match ifd_map.get(&317) {
+        Some(ifd) => ifd.interpret_as_u16()[0],
+        _ => PREDICTOR_NONE,
+    };
+
     let photometric_interp = match ifd_map.get(&262) {
         Some(ifd) => ifd.interpret_as_u16()[0],
         _ => {
@@ -615,6 +940,22 @@ pub fn read_geotiff<'a>(
         _ => {}
     }
 
+    if overview_scale != (1f64, 1f64) {
+        let (sx, sy) = overview_scale;
+        configs.model_pixel_scale[0] *= sx;
+        configs.model_pixel_scale[1] *= sy;
+        for b in (0..configs.model_tiepoint.len() / 6).map(|a| a * 6) {
+            configs.model_tiepoint[b] /= sx;
+            configs.model_tiepoint[b + 1] /= sy;
+        }
+        if configs.model_transformation[0] != 0.0 {
+            configs.model_transformation[0] *= sx;
+            configs.model_transformation[4] *= sx;
+            configs.model_transformation[1] *= sy;
+            configs.model_transformation[5] *= sy;
+        }
+    }
+
     if configs.model_tiepoint.len() == 6 {
         // see if the model_pixel_scale tag was actually specified
         if configs.model_pixel_scale[0] == 0.0 {
@@ -1083,6 +1424,48 @@ pub fn read_geotiff<'a>(
         };
     }
 
+    let data = match data {
+        Some(d) => d,
+        None => {
+            // Only the header and cell layout are wanted.
+            if compression != COMPRESS_NONE
+                || mode != IM_GRAY
+                || bits_per_sample.len() != 1
+                || predictor != PREDICTOR_NONE
+                || !(1..=3).contains(&sample_format[0])
+                || ![8, 16, 32, 64].contains(&bits_per_sample[0])
+                || (sample_format[0] == 3 && bits_per_sample[0] < 32)
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Only uncompressed, single-band GeoTIFFs can be read without decoding.",
+                ));
+            }
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            configs.data_type = match (sample_format[0], bits_per_sample[0]) {
+                (1, 8) => DataType::U8,
+                (1, 16) => DataType::U16,
+                (1, 32) => DataType::U32,
+                (1, _) => DataType::U64,
+                (2, 8) => DataType::I8,
+                (2, 16) => DataType::I16,
+                (2, 32) => DataType::I32,
+                (2, _) => DataType::I64,
+                (_, 32) => DataType::F32,
+                _ => DataType::F64,
+            };
+            return Ok(Some(GeoTiffBlockLayout {
+                block_width,
+                block_height,
+                blocks_across,
+                block_offsets,
+                block_counts,
+                bits_per_sample: bits_per_sample[0],
+                sample_format: sample_format[0],
+            }));
+        }
+    };
+
     ////////////////////
     // Read the data! //
     ////////////////////
@@ -1153,13 +1536,29 @@ pub fn read_geotiff<'a>(
                         // decoder.read_to_end(&mut buf).unwrap();
                         buf.extend(decompress_to_vec_zlib(&compressed).expect("Error encountered while decoding the DEFLATE compressed GeoTIFF file."));
                     }
+                    COMPRESS_ZSTD => {
+                        th.seek(offset);
+                        let mut compressed = vec![0u8; n];
+                        th.read_exact(&mut compressed).expect("Error reading bytes from file.");
+                        buf = compression::zstd_decompress(&compressed)?;
+                    }
                     _ => {
                         return Err(Error::new(
                             ErrorKind::InvalidData,
-                            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS and DEFLATE compression.",
+                            "The WhiteboxTools GeoTIFF decoder currently only supports PACKBITS, LZW, DEFLATE, and ZSTD compression.",
                         ))
                     }
                 }
+
+                // The predictor is applied to each row of the block, which is blk_w pixels wide.
+                compression::undo_predictor(
+                    &mut buf,
+                    predictor,
+                    blk_w,
+                    bits_per_sample.len(),
+                    bits_per_sample[0] as usize / 8,
+                    configs.endian,
+                )?;
             }
 
             // println!("{:?}", &buf[0..8]);
@@ -1373,6 +1772,9 @@ pub fn read_geotiff<'a>(
                                 value = bor.read_u8()? as usize;
                                 data[i] = palette[value] as f64;
                             }
+                            if skip_bytes > 0 {
+                                bor.inc_pos(skip_bytes);
+                            }
                         }
                     }
                     IM_RGB => {
@@ -1389,6 +1791,9 @@ pub fn read_geotiff<'a>(
                                     i = y * width + x;
                                     data[i] = value as f64;
                                 }
+                                if skip_bytes > 0 {
+                                    bor.inc_pos(skip_bytes * 3);
+                                }
                             }
                         } else if bits_per_sample[0] == 16 {
                             // the spec doesn't talk about 16-bit RGB images so
@@ -1407,6 +1812,9 @@ pub fn read_geotiff<'a>(
                                     i = y * width + x;
                                     data[i] = value as f64;
                                 }
+                                if skip_bytes > 0 {
+                                    bor.inc_pos(skip_bytes * 6);
+                                }
                             }
                         } else {
                             return Err(Error::new(
@@ -1429,6 +1837,9 @@ pub fn read_geotiff<'a>(
                                     i = y * width + x;
                                     data[i] = value as f64;
                                 }
+                                if skip_bytes > 0 {
+                                    bor.inc_pos(skip_bytes * 4);
+                                }
                             }
                         } else if bits_per_sample[0] == 16 {
                             // the spec doesn't talk about 16-bit RGB images so
@@ -1447,6 +1858,9 @@ pub fn read_geotiff<'a>(
                                     i = y * width + x;
                                     data[i] = value as f64;
                                 }
+                                if skip_bytes > 0 {
+                                    bor.inc_pos(skip_bytes * 8);
+                                }
                             }
                         } else {
                             return Err(Error::new(
@@ -1602,61 +2016,6 @@ pub fn read_geotiff<'a>(
         }
     }
 
-    // Check to see if a predictor is used with LZW and DEFLATE
-    match ifd_map.get(&317) {
-        Some(ifd) => {
-            if ifd.interpret_as_u16()[0] == 2 {
-                // Horizontal predictor
-                // transform the data
-                let mut idx: usize;
-                for row in 0..configs.rows {
-                    for col in 1..configs.columns {
-                        idx = row * configs.columns + col;
-                        data[idx] += data[idx - 1];
-                    }
-                }
-            }
-            // if ifd.interpret_as_u16()[0] == 3 {
-            //     if configs.endian == Endianness::LittleEndian {
-            //         if configs.data_type == DataType::F32 {
-            //             let mut in_val: f32;
-            //             let mut out_val: f32;
-            //             let mut in_bytes: [u8; 4];
-            //             let mut out_bytes = [0u8; 4];
-            //             let mut idx: usize;
-            //             for row in 0..configs.rows {
-            //                 for col in 1..configs.columns {
-            //                     idx = row * configs.columns + col;
-            //                     in_val = data[idx] as f32;
-            //                     in_bytes = in_val.to_le_bytes();
-            //                     for b in 0..4 {
-            //                         out_bytes[4-b-1] = in_bytes[b];
-            //                     }
-            //                     out_val = f32::from_le_bytes(out_bytes);
-            //                     data[idx] = out_val as f64;
-            //                 }
-            //             }
-            //         } else { // F64
-
-            //         }
-            //     } else {
-            //         if configs.data_type == DataType::F32 {
-
-            //         } else { // F64
-
-            //         }
-            //     }
-
-            if ifd.interpret_as_u16()[0] == 3 {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    "The GeoTIFF reader does not currently support floating-point predictors (PREDICTOR=3).",
-                ));
-            }
-        }
-        _ => {} // do nothing,
-    }
-
     // match geokeys_map.get(&1024) {
     //     Some(ifd) => geokeys.add_key_directory(&ifd.data),
     //     _ => return Err(Error::new(ErrorKind::InvalidData, "The TIFF file does not contain geokeys")),
@@ -1673,15 +2032,23 @@ pub fn read_geotiff<'a>(
         map_sorter.push(key);
     }
 
-    Ok(())
+    Ok(None)
 }
 
 pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     // We'll need to look at the configurations to see if compression should be used
     let configs = whitebox_common::configs::get_configs()?;
-    let use_compression = configs.compress_rasters;
+    let compression = TiffCompression::from_configs(&configs)?;
+
+    if configs.cog {
+        return cog::write_cog(r, compression, configs.geotiff_predictor);
+    }
+
+    // Compressed output is tiled, which compresses far better than rows of cells.
+    if compression != TiffCompression::None {
+        return cog::write_tiled(r, compression, configs.geotiff_predictor);
+    }
 
-    
     // get the ByteOrderWriter
     let f = File::create(r.file_name.clone())?;
     let mut writer = BufWriter::new(f);
@@ -1700,31 +2067,22 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         ));
     }
 
-    // is it a BigTiff?
-    let is_big_tiff = if 8usize
-        + (r.configs.rows * r.configs.columns) as usize * total_bytes_per_pixel
-        >= 4_000_000_000
-    {
-        true
-    } else {
-        false
-    };
+    // is it a BigTiff? Each row is a strip, with a 4-byte offset and byte count in a classic TIFF.
+    let is_big_tiff = requires_big_tiff(
+        8u64 + (r.configs.rows * r.configs.columns) as u64 * total_bytes_per_pixel as u64
+            + r.configs.rows as u64 * 8u64,
+    );
 
     let header_size = if !is_big_tiff { 8u64 } else { 16u64 };
 
     // get the offset to the first ifd
     let mut ifd_start_needs_extra_byte = false;
-    let mut ifd_start = if !use_compression {
-        let mut val = header_size
-            + (r.configs.rows * r.configs.columns) as u64 * total_bytes_per_pixel as u64;
-        if val % 2 == 1 {
-            val += 1;
-            ifd_start_needs_extra_byte = true;
-        }
-        val
-    } else {
-        0u64
-    };
+    let mut ifd_start = header_size
+        + (r.configs.rows * r.configs.columns) as u64 * total_bytes_per_pixel as u64;
+    if ifd_start % 2 == 1 {
+        ifd_start += 1;
+        ifd_start_needs_extra_byte = true;
+    }
 
     //////////////////////
     // Write the header //
@@ -1743,564 +2101,157 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     } else {
         // magic number
         write_u16(&mut writer, r.configs.endian, 43u16).expect("Error writing byte data.");
-        // Bytesize of offsets
-        write_u16(&mut writer, r.configs.endian, 8u16).expect("Error writing byte data.");
-
-        write_u16(&mut writer, r.configs.endian, 0u16).expect("Error writing byte data."); // Always 0
-
-        // offset to first IFD
-        write_u64(&mut writer, r.configs.endian, ifd_start).expect("Error writing byte data.");
-    }
-
-    // At the moment, categorical and paletted output is not supported.
-    if r.configs.photometric_interp == PhotometricInterpretation::Categorical
-        || r.configs.photometric_interp == PhotometricInterpretation::Paletted
-    {
-        r.configs.photometric_interp = PhotometricInterpretation::Continuous;
-    }
-
-    //////////////////////////
-    // Write the image data //
-    //////////////////////////
-    let mut strip_offsets = vec![];
-    let mut strip_byte_counts = vec![];
-    let mut current_offset = header_size;
-    if use_compression {
-        // DEFLATE is the only supported compression method at present
-
-        // let mut current_offset = header_size;
-        let mut row_length_in_bytes: u64;
-        match r.configs.photometric_interp {
-            PhotometricInterpretation::Continuous
-            | PhotometricInterpretation::Categorical
-            | PhotometricInterpretation::Boolean => match r.configs.data_type {
-                DataType::F64 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns * 8);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_f64::<LittleEndian>(r.data[i] as f64)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_f64::<BigEndian>(r.data[i] as f64)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                DataType::F32 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns * 4);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_f32::<LittleEndian>(r.data[i] as f32)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_f32::<BigEndian>(r.data[i] as f32)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                DataType::U64 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns * 8);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_u64::<LittleEndian>(r.data[i] as u64)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_u64::<BigEndian>(r.data[i] as u64)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                DataType::U32 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns * 4);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_u32::<LittleEndian>(r.data[i] as u32)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_u32::<BigEndian>(r.data[i] as u32)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                DataType::U16 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns * 2);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_u16::<LittleEndian>(r.data[i] as u16)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_u16::<BigEndian>(r.data[i] as u16)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                DataType::U8 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_u8(r.data[i] as u8)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_u8(r.data[i] as u8)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                DataType::I64 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns * 8);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_i64::<LittleEndian>(r.data[i] as i64)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_i64::<BigEndian>(r.data[i] as i64)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                DataType::I32 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns * 4);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_i32::<LittleEndian>(r.data[i] as i32)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_i32::<BigEndian>(r.data[i] as i32)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                DataType::I16 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns * 2);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_i16::<LittleEndian>(r.data[i] as i16)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_i16::<BigEndian>(r.data[i] as i16)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                DataType::I8 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        let mut data = Vec::with_capacity(r.configs.columns);
-                        if r.configs.endian == Endianness::LittleEndian {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_i8(r.data[i] as i8)
-                                    .expect("Error writing byte data.");
-                            }
-                        } else {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                data.write_i8(r.data[i] as i8)
-                                    .expect("Error writing byte data.");
-                            }
-                        }
-                        // compress the data vec
-                        let compressed = compress_to_vec_zlib(&data, 6);
-                        write_bytes(&mut writer, &compressed)
-                            .expect("Error writing byte data to file.");
-                        row_length_in_bytes = compressed.len() as u64;
-                        strip_byte_counts.push(row_length_in_bytes);
-                        strip_offsets.push(current_offset);
-                        current_offset += row_length_in_bytes;
-                        if row_length_in_bytes % 2 != 0 {
-                            // This is just because the data must start on a word (i.e. an even value).
-                            write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                            current_offset += 1;
-                        }
-                    }
-                }
-                _ => {
-                    return Err(Error::new(
-                        ErrorKind::InvalidData,
-                        format!(
-                            "Unknown data type: {:?}. Photomet interp: {:?}",
-                            r.configs.data_type, r.configs.photometric_interp
-                        ),
-                    ));
-                }
-            },
-            PhotometricInterpretation::RGB => {
-                match r.configs.data_type {
-                    DataType::RGB24 => {
-                        // let mut bytes: [u8; 3] = [0u8; 3];
-                        let mut i: usize;
-                        let mut val: u32;
-                        for row in 0..r.configs.rows {
-                            let mut data = Vec::with_capacity(r.configs.columns * 3);
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                val = r.data[i] as u32;
-                                data.write_u8((val & 0xFF) as u8)
-                                    .expect("Error writing byte data."); // red
-
-                                data.write_u8(((val >> 8u32) & 0xFF) as u8)
-                                    .expect("Error writing byte data."); // green
+        // Bytesize of offsets
+        write_u16(&mut writer, r.configs.endian, 8u16).expect("Error writing byte data.");
 
-                                data.write_u8(((val >> 16u32) & 0xFF) as u8)
-                                    .expect("Error writing byte data."); // blue
-                            }
-                            // compress the data vec
-                            let compressed = compress_to_vec_zlib(&data, 6);
-                            write_bytes(&mut writer, &compressed)
-                                .expect("Error writing byte data to file.");
-                            row_length_in_bytes = compressed.len() as u64;
-                            strip_byte_counts.push(row_length_in_bytes);
-                            strip_offsets.push(current_offset);
-                            current_offset += row_length_in_bytes;
-                            if row_length_in_bytes % 2 != 0 {
-                                // This is just because the data must start on a word (i.e. an even value).
-                                write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                                current_offset += 1;
-                            }
-                        }
-                    }
-                    DataType::RGBA32 | DataType::U32 => {
-                        let mut i: usize;
-                        // let mut bytes: [u8; 4] = [0u8; 4];
-                        let mut val: u32;
-                        for row in 0..r.configs.rows {
-                            let mut data = Vec::with_capacity(r.configs.columns * 4);
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                val = r.data[i] as u32;
-                                data.write_u8((val & 0xFF) as u8)
-                                    .expect("Error writing byte data."); // red
+        write_u16(&mut writer, r.configs.endian, 0u16).expect("Error writing byte data."); // Always 0
 
-                                data.write_u8(((val >> 8u32) & 0xFF) as u8)
-                                    .expect("Error writing byte data."); // green
+        // offset to first IFD
+        write_u64(&mut writer, r.configs.endian, ifd_start).expect("Error writing byte data.");
+    }
 
-                                data.write_u8(((val >> 16u32) & 0xFF) as u8)
-                                    .expect("Error writing byte data."); // blue
+    // At the moment, categorical and paletted output is not supported.
+    if r.configs.photometric_interp == PhotometricInterpretation::Categorical
+        || r.configs.photometric_interp == PhotometricInterpretation::Paletted
+    {
+        r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+    }
 
-                                data.write_u8(((val >> 24u32) & 0xFF) as u8)
-                                    .expect("Error writing byte data."); // a
-                            }
-                            // for col in 0..r.configs.columns {
-                            //     i = row * r.configs.columns + col;
-                            //     val = r.data[i] as u32;
-                            //     bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
-                            //     bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
-                            //     bytes[0] = (val & 0xFF) as u8; // red
-                            //     bytes[3] = ((val >> 24u32) & 0xFF) as u8; // a
-                            //     write_bytes(&mut writer, &bytes)?;
-                            // }
-
-                            // compress the data vec
-                            let compressed = compress_to_vec_zlib(&data, 6);
-                            write_bytes(&mut writer, &compressed)
-                                .expect("Error writing byte data to file.");
-                            row_length_in_bytes = compressed.len() as u64;
-                            strip_byte_counts.push(row_length_in_bytes);
-                            strip_offsets.push(current_offset);
-                            current_offset += row_length_in_bytes;
-                            if row_length_in_bytes % 2 != 0 {
-                                // This is just because the data must start on a word (i.e. an even value).
-                                write_u8(&mut writer, 0u8).expect("Error writing data to file.");
-                                current_offset += 1;
-                            }
-                        }
-                    }
-                    _ => {
-                        return Err(Error::new(
-                            ErrorKind::InvalidData,
-                            format!(
-                                "Unknown data type: {:?}. Photomet interp: {:?}",
-                                r.configs.data_type, r.configs.photometric_interp
-                            ),
-                        ));
+    //////////////////////////
+    // Write the image data //
+    //////////////////////////
+    match r.configs.photometric_interp {
+        PhotometricInterpretation::Continuous
+        | PhotometricInterpretation::Categorical
+        | PhotometricInterpretation::Boolean => match r.configs.data_type {
+            DataType::F64 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_f64(&mut writer, r.configs.endian, r.data[i])?;
                     }
                 }
             }
-            PhotometricInterpretation::Paletted => {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    "Paletted GeoTIFFs are currently unsupported for writing.",
-                ));
-            }
-            PhotometricInterpretation::Unknown => {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    "Error while writing GeoTIFF file.",
-                ));
+            DataType::F32 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_f32(&mut writer, r.configs.endian, r.data[i] as f32)?;
+                    }
+                }
             }
-        }
-    } else {
-        match r.configs.photometric_interp {
-            PhotometricInterpretation::Continuous
-            | PhotometricInterpretation::Categorical
-            | PhotometricInterpretation::Boolean => match r.configs.data_type {
-                DataType::F64 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            write_f64(&mut writer, r.configs.endian, r.data[i])?;
-                        }
+            DataType::U64 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_u64(&mut writer, r.configs.endian, r.data[i] as u64)?;
                     }
                 }
-                DataType::F32 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            write_f32(&mut writer, r.configs.endian, r.data[i] as f32)?;
-                        }
+            }
+            DataType::U32 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_u32(&mut writer, r.configs.endian, r.data[i] as u32)?;
                     }
                 }
-                DataType::U64 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            write_u64(&mut writer, r.configs.endian, r.data[i] as u64)?;
-                        }
+            }
+            DataType::U16 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_u16(&mut writer, r.configs.endian, r.data[i] as u16)?;
                     }
                 }
-                DataType::U32 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            write_u32(&mut writer, r.configs.endian, r.data[i] as u32)?;
-                        }
+            }
+            DataType::U8 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_u8(&mut writer, r.data[i] as u8)?;
                     }
                 }
-                DataType::U16 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            write_u16(&mut writer, r.configs.endian, r.data[i] as u16)?;
-                        }
+            }
+            DataType::I64 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_i64(&mut writer, r.configs.endian, r.data[i] as i64)?;
                     }
                 }
-                DataType::U8 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            write_u8(&mut writer, r.data[i] as u8)?;
-                        }
+            }
+            DataType::I32 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_i32(&mut writer, r.configs.endian, r.data[i] as i32)?;
                     }
                 }
-                DataType::I64 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            write_i64(&mut writer, r.configs.endian, r.data[i] as i64)?;
-                        }
+            }
+            DataType::I16 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_i16(&mut writer, r.configs.endian, r.data[i] as i16)?;
                     }
                 }
-                DataType::I32 => {
-                    let mut i: usize;
-                    for row in 0..r.configs.rows {
-                        for col in 0..r.configs.columns {
-                            i = row * r.configs.columns + col;
-                            write_i32(&mut writer, r.configs.endian, r.data[i] as i32)?;
-                        }
+            }
+            DataType::I8 => {
+                let mut i: usize;
+                for row in 0..r.configs.rows {
+                    for col in 0..r.configs.columns {
+                        i = row * r.configs.columns + col;
+                        write_i8(&mut writer, r.data[i] as i8)?;
                     }
                 }
-                DataType::I16 => {
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "Unknown data type: {:?}. Photomet interp: {:?}",
+                        r.configs.data_type, r.configs.photometric_interp
+                    ),
+                ));
+            }
+        },
+        PhotometricInterpretation::RGB => {
+            match r.configs.data_type {
+                DataType::RGB24 => {
+                    let mut bytes: [u8; 3] = [0u8; 3];
                     let mut i: usize;
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_i16(&mut writer, r.configs.endian, r.data[i] as i16)?;
+                            let val = r.data[i] as u32;
+                            bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
+                            bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
+                            bytes[0] = (val & 0xFF) as u8; // red
+                            write_bytes(&mut writer, &bytes)?;
                         }
                     }
                 }
-                DataType::I8 => {
+                DataType::RGBA32 | DataType::U32 => {
                     let mut i: usize;
+                    let mut bytes: [u8; 4] = [0u8; 4];
                     for row in 0..r.configs.rows {
                         for col in 0..r.configs.columns {
                             i = row * r.configs.columns + col;
-                            write_i8(&mut writer, r.data[i] as i8)?;
+                            let val = r.data[i] as u32;
+                            bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
+                            bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
+                            bytes[0] = (val & 0xFF) as u8; // red
+                            bytes[3] = ((val >> 24u32) & 0xFF) as u8; // a
+                            write_bytes(&mut writer, &bytes)?;
                         }
                     }
                 }
@@ -2313,78 +2264,20 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                         ),
                     ));
                 }
-            },
-            PhotometricInterpretation::RGB => {
-                match r.configs.data_type {
-                    DataType::RGB24 => {
-                        let mut bytes: [u8; 3] = [0u8; 3];
-                        let mut i: usize;
-                        for row in 0..r.configs.rows {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                let val = r.data[i] as u32;
-                                bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
-                                bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
-                                bytes[0] = (val & 0xFF) as u8; // red
-                                write_bytes(&mut writer, &bytes)?;
-                            }
-                        }
-                    }
-                    DataType::RGBA32 | DataType::U32 => {
-                        let mut i: usize;
-                        let mut bytes: [u8; 4] = [0u8; 4];
-                        for row in 0..r.configs.rows {
-                            for col in 0..r.configs.columns {
-                                i = row * r.configs.columns + col;
-                                let val = r.data[i] as u32;
-                                bytes[2] = ((val >> 16u32) & 0xFF) as u8; // blue
-                                bytes[1] = ((val >> 8u32) & 0xFF) as u8; // green
-                                bytes[0] = (val & 0xFF) as u8; // red
-                                bytes[3] = ((val >> 24u32) & 0xFF) as u8; // a
-                                write_bytes(&mut writer, &bytes)?;
-                            }
-                        }
-                    }
-                    _ => {
-                        return Err(Error::new(
-                            ErrorKind::InvalidData,
-                            format!(
-                                "Unknown data type: {:?}. Photomet interp: {:?}",
-                                r.configs.data_type, r.configs.photometric_interp
-                            ),
-                        ));
-                    }
-                }
-            }
-            PhotometricInterpretation::Paletted => {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    "Paletted GeoTIFFs are currently unsupported for writing.",
-                ));
-            }
-            PhotometricInterpretation::Unknown => {
-                return Err(Error::new(
-                    ErrorKind::InvalidData,
-                    "Error while writing GeoTIFF file.",
-                ));
             }
         }
-    }
-
-    if use_compression {
-        ifd_start = current_offset; // header_size + strip_byte_counts.iter().sum();
-        if ifd_start % 2 == 1 {
-            ifd_start += 1;
-            ifd_start_needs_extra_byte = true;
+        PhotometricInterpretation::Paletted => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Paletted GeoTIFFs are currently unsupported for writing.",
+            ));
         }
-        if !is_big_tiff {
-            let _ = writer.seek(SeekFrom::Start(4));
-            write_u32(&mut writer, r.configs.endian, ifd_start as u32)?;
-        } else {
-            let _ = writer.seek(SeekFrom::Start(8));
-            write_u64(&mut writer, r.configs.endian, ifd_start)?;
+        PhotometricInterpretation::Unknown => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Error while writing GeoTIFF file.",
+            ));
         }
-        let _ = writer.seek(SeekFrom::End(0));
     }
 
     // This is just because the IFD must start on a word (i.e. an even value). If the data are
@@ -2545,21 +2438,12 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     }
 
     // Compression tag (259)
-    if use_compression {
-        ifd_entries.push(Entry::new(
-            TAG_COMPRESSION,
-            DT_SHORT,
-            1u64,
-            COMPRESS_DEFLATE as u64,
-        ));
-    } else {
-        ifd_entries.push(Entry::new(
-            TAG_COMPRESSION,
-            DT_SHORT,
-            1u64,
-            COMPRESS_NONE as u64,
-        ));
-    }
+    ifd_entries.push(Entry::new(
+        TAG_COMPRESSION,
+        DT_SHORT,
+        1u64,
+        COMPRESS_NONE as u64,
+    ));
 
     // PhotometricInterpretation tag (262)
     let pi = match r.configs.photometric_interp {
@@ -2589,19 +2473,11 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             r.configs.rows as u64,
             larger_values_data.len() as u64,
         ));
-        if use_compression {
-            for val in strip_offsets {
-                larger_values_data
-                    .write_u32(val as u32)
-                    .expect("Error writing the TIFF strip offsets tag");
-            }
-        } else {
-            let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel as u32;
-            for i in 0..r.configs.rows as u32 {
-                larger_values_data
-                    .write_u32(header_size as u32 + row_length_in_bytes * i)
-                    .expect("Error writing the TIFF strip offsets tag");
-            }
+        let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel as u32;
+        for i in 0..r.configs.rows as u32 {
+            larger_values_data
+                .write_u32(header_size as u32 + row_length_in_bytes * i)
+                .expect("Error writing the TIFF strip offsets tag");
         }
     } else {
         ifd_entries.push(Entry::new(
@@ -2610,19 +2486,11 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             r.configs.rows as u64,
             larger_values_data.len() as u64,
         ));
-        if use_compression {
-            for val in strip_offsets {
-                larger_values_data
-                    .write_u64(val)
-                    .expect("Error writing the TIFF strip offsets tag");
-            }
-        } else {
-            let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel as u64;
-            for i in 0..r.configs.rows as u64 {
-                larger_values_data
-                    .write_u64(header_size + row_length_in_bytes * i)
-                    .expect("Error writing the TIFF strip offsets");
-            }
+        let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel as u64;
+        for i in 0..r.configs.rows as u64 {
+            larger_values_data
+                .write_u64(header_size + row_length_in_bytes * i)
+                .expect("Error writing the TIFF strip offsets");
         }
     }
     // if !is_big_tiff {
@@ -2680,19 +2548,11 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
             }
         };
-        if use_compression {
-            for val in strip_byte_counts {
-                larger_values_data
-                    .write_u32(val as u32)
-                    .expect("Error writing the TIFF strip byte counts tag");
-            }
-        } else {
-            let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel;
-            for _ in 0..r.configs.rows as u32 {
-                larger_values_data
-                    .write_u32(row_length_in_bytes)
-                    .expect("Error writing the TIFF strip byte counts tag");
-            }
+        let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel;
+        for _ in 0..r.configs.rows as u32 {
+            larger_values_data
+                .write_u32(row_length_in_bytes)
+                .expect("Error writing the TIFF strip byte counts tag");
         }
     } else {
         ifd_entries.push(Entry::new(
@@ -2713,19 +2573,11 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
             }
         };
-        if use_compression {
-            for val in strip_byte_counts {
-                larger_values_data
-                    .write_u64(val)
-                    .expect("Error writing the TIFF strip byte counts tag");
-            }
-        } else {
-            let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel;
-            for _ in 0..r.configs.rows as u32 {
-                larger_values_data
-                    .write_u64(row_length_in_bytes)
-                    .expect("Error writing the TIFF strip byte counts tag");
-            }
+        let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel;
+        for _ in 0..r.configs.rows as u32 {
+            larger_values_data
+                .write_u64(row_length_in_bytes)
+                .expect("Error writing the TIFF strip byte counts tag");
         }
     }
     /*
@@ -2778,6 +2630,39 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
     }
     */
 
+    push_descriptive_entries(
+        r,
+        is_big_tiff,
+        samples_per_pixel,
+        &mut ifd_entries,
+        &mut larger_values_data,
+    )?;
+
+    ///////////////////
+    // Write the IFD //
+    ///////////////////
+    write_ifd(
+        &mut writer,
+        r.configs.endian,
+        is_big_tiff,
+        ifd_start,
+        ifd_entries,
+        larger_values_data.get_inner(),
+        0u64,
+    )?;
+
+    Ok(())
+}
+
+/// Adds the entries describing the sample format, georeferencing, nodata value, and geokeys of
+/// a raster to the entries of an IFD.
+fn push_descriptive_entries(
+    r: &Raster,
+    is_big_tiff: bool,
+    samples_per_pixel: u16,
+    ifd_entries: &mut Vec<Entry>,
+    larger_values_data: &mut ByteOrderWriter<Vec<u8>>,
+) -> Result<(), Error> {
     // There is currently no support for storing the image resolution, so give a bogus value of 72x72 dpi.
     // XResolution tag (282)
     ifd_entries.push(Entry::new(
@@ -3263,49 +3148,62 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         }
     }
 
-    ///////////////////
-    // Write the IFD //
-    ///////////////////
+    Ok(())
+}
 
+/// Writes an IFD starting at `ifd_start`, followed by the values of its entries that do not fit
+/// within the entries themselves. `next_ifd` is the offset of the following IFD, or 0 if it is
+/// the last IFD of the file.
+fn write_ifd<W: Write>(
+    writer: &mut BufWriter<W>,
+    endian: Endianness,
+    is_big_tiff: bool,
+    ifd_start: u64,
+    mut ifd_entries: Vec<Entry>,
+    larger_values_data: &[u8],
+    next_ifd: u64,
+) -> Result<(), Error> {
     // Number of Directory Entries.
     if !is_big_tiff {
-        write_u16(&mut writer, r.configs.endian, ifd_entries.len() as u16)?;
+        write_u16(writer, endian, ifd_entries.len() as u16)?;
 
         // Sort the IFD entries
         ifd_entries.sort_by(|a, b| a.tag.cmp(&b.tag));
 
         // Write the entries
         let ifd_length = 2u64 + ifd_entries.len() as u64 * 12u64 + 4u64;
+        if ifd_start + ifd_length + larger_values_data.len() as u64 > CLASSIC_TIFF_MAX_OFFSET {
+            return Err(classic_tiff_overflow());
+        }
 
         for ifde in ifd_entries {
-            write_u16(&mut writer, r.configs.endian, ifde.tag)?; // Tag
-            write_u16(&mut writer, r.configs.endian, ifde.ifd_type)?; // Field type
-            write_u32(&mut writer, r.configs.endian, ifde.num_values as u32)?; // Num of values
+            write_u16(writer, endian, ifde.tag)?; // Tag
+            write_u16(writer, endian, ifde.ifd_type)?; // Field type
+            write_u32(writer, endian, ifde.num_values as u32)?; // Num of values
             if ifde.ifd_type == DT_SHORT && ifde.num_values == 1 {
                 // it's a value
-                write_u16(&mut writer, r.configs.endian, ifde.offset as u16)?; // Value
-                write_u16(&mut writer, r.configs.endian, 0u16)?; // Fill the remaining 2 right bytes of the u32
+                write_u16(writer, endian, ifde.offset as u16)?; // Value
+                write_u16(writer, endian, 0u16)?; // Fill the remaining 2 right bytes of the u32
             } else if ifde.ifd_type == DT_LONG && ifde.num_values == 1 {
                 // it's a value
-                write_u32(&mut writer, r.configs.endian, ifde.offset as u32)?;
+                write_u32(writer, endian, ifde.offset as u32)?;
             } else if ifde.ifd_type == DT_SHORT && ifde.num_values == 2 {
                 // I'm not really sure about this one. Two shorts will fit in the value_offset, but will they be interpreted correctly?
-                write_u32(&mut writer, r.configs.endian, ifde.offset as u32)?; // Value
+                write_u32(writer, endian, ifde.offset as u32)?; // Value
             } else {
                 // it's an offset
                 write_u32(
-                    &mut writer,
-                    r.configs.endian,
+                    writer,
+                    endian,
                     ifd_start as u32 + ifd_length as u32 + ifde.offset as u32,
                 )?;
             }
         }
 
-        // 4-byte offset of the next IFD; Note, only single image TIFFs are currently supported
-        // and therefore, this will always be set to '0'.
-        write_u32(&mut writer, r.configs.endian, 0u32)?;
+        // 4-byte offset of the next IFD, or 0 if this is the last IFD.
+        write_u32(writer, endian, next_ifd as u32)?;
     } else {
-        write_u64(&mut writer, r.configs.endian, ifd_entries.len() as u64)?;
+        write_u64(writer, endian, ifd_entries.len() as u64)?;
 
         // Sort the IFD entries
         ifd_entries.sort_by(|a, b| a.tag.cmp(&b.tag));
@@ -3314,46 +3212,45 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
         let ifd_length = 8u64 + ifd_entries.len() as u64 * 20u64 + 8u64;
 
         for ifde in ifd_entries {
-            write_u16(&mut writer, r.configs.endian, ifde.tag)?; // Tag
-            write_u16(&mut writer, r.configs.endian, ifde.ifd_type)?; // Field type
-            write_u64(&mut writer, r.configs.endian, ifde.num_values)?; // Num of values
+            write_u16(writer, endian, ifde.tag)?; // Tag
+            write_u16(writer, endian, ifde.ifd_type)?; // Field type
+            write_u64(writer, endian, ifde.num_values)?; // Num of values
             if ifde.ifd_type == DT_SHORT && ifde.num_values == 1 {
                 // it's a value
-                write_u16(&mut writer, r.configs.endian, ifde.offset as u16)?; // Value
-                write_u16(&mut writer, r.configs.endian, 0u16)?; // Fill the remaining bytes of the u64
-                write_u32(&mut writer, r.configs.endian, 0u32)?; // Fill the remaining bytes of the u64
+                write_u16(writer, endian, ifde.offset as u16)?; // Value
+                write_u16(writer, endian, 0u16)?; // Fill the remaining bytes of the u64
+                write_u32(writer, endian, 0u32)?; // Fill the remaining bytes of the u64
             } else if ifde.ifd_type == DT_SHORT && ifde.num_values == 2 {
                 // I'm not really sure about this one. Two shorts will fit in the value_offset, but will they be interpreted correctly?
-                write_u32(&mut writer, r.configs.endian, ifde.offset as u32)?; // Value
-                write_u32(&mut writer, r.configs.endian, 0u32)?; // Fill the remaining bytes of the u64
+                write_u32(writer, endian, ifde.offset as u32)?; // Value
+                write_u32(writer, endian, 0u32)?; // Fill the remaining bytes of the u64
             } else if ifde.ifd_type == DT_LONG && ifde.num_values == 1 {
                 // it's a value
-                write_u32(&mut writer, r.configs.endian, ifde.offset as u32)?;
-                write_u32(&mut writer, r.configs.endian, 0u32)?; // Fill the remaining bytes of the u64
+                write_u32(writer, endian, ifde.offset as u32)?;
+                write_u32(writer, endian, 0u32)?; // Fill the remaining bytes of the u64
             } else if (ifde.ifd_type == DT_LONG && ifde.num_values == 2)
                 || (ifde.ifd_type == DT_TIFF_LONG8 && ifde.num_values == 1)
             {
                 // it's a value
-                write_u64(&mut writer, r.configs.endian, ifde.offset)?;
+                write_u64(writer, endian, ifde.offset)?;
             } else {
                 // it's an offset
                 write_u64(
-                    &mut writer,
-                    r.configs.endian,
+                    writer,
+                    endian,
                     ifd_start + ifd_length + ifde.offset,
                 )?;
             }
         }
 
-        // 4-byte offset of the next IFD; Note, only single image TIFFs are currently supported
-        // and therefore, this will always be set to '0'.
-        write_u64(&mut writer, r.configs.endian, 0u64)?;
+        // 8-byte offset of the next IFD, or 0 if this is the last IFD.
+        write_u64(writer, endian, next_ifd)?;
     }
 
     //////////////////////////////////
     // Write the larger_values_data //
     //////////////////////////////////
-    write_bytes(&mut writer, larger_values_data.get_inner())?;
+    write_bytes(writer, larger_values_data)?;
 
     Ok(())
 }
@@ -4030,21 +3927,9 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             r.configs.rows as u64,
             larger_values_data.len() as u64,
         ));
-        if use_compression {
-            let mut i = 0;
-            println!("Strip Offsets:");
-            for val in strip_offsets {
-                if i < 100 {
-                    println!("{} {}", i, val);
-                    i += 1;
-                }
-                larger_values_data.write_u32(val as u32).expect("Error writing the TIFF strip offsets tag");
-            }
-        } else {
-            let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel as u32;
-            for i in 0..r.configs.rows as u32 {
-                larger_values_data.write_u32(header_size as u32 + row_length_in_bytes * i).expect("Error writing the TIFF strip offsets tag");
-            }
+        let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel as u32;
+        for i in 0..r.configs.rows as u32 {
+            larger_values_data.write_u32(header_size as u32 + row_length_in_bytes * i).expect("Error writing the TIFF strip offsets tag");
         }
     } else {
         ifd_entries.push(Entry::new(
@@ -4053,15 +3938,9 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
             r.configs.rows as u64,
             larger_values_data.len() as u64,
         ));
-        if use_compression {
-            for val in strip_offsets {
-                larger_values_data.write_u64(val).expect("Error writing the TIFF strip offsets tag");
-            }
-        } else {
-            let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel as u64;
-            for i in 0..r.configs.rows as u64 {
-                larger_values_data.write_u64(header_size + row_length_in_bytes * i).expect("Error writing the TIFF strip offsets");
-            }
+        let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel as u64;
+        for i in 0..r.configs.rows as u64 {
+            larger_values_data.write_u64(header_size + row_length_in_bytes * i).expect("Error writing the TIFF strip offsets");
         }
     }
 
@@ -4096,21 +3975,9 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
             }
         };
-        if use_compression {
-            let mut i = 0;
-            println!("Strip Byte Counts:");
-            for val in strip_byte_counts {
-                if i < 100 {
-                    println!("{} {}", i, val);
-                    i += 1;
-                }
-                larger_values_data.write_u32(val as u32).expect("Error writing the TIFF strip byte counts tag");
-            }
-        } else {
-            let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel;
-            for _ in 0..r.configs.rows as u32 {
-                larger_values_data.write_u32(row_length_in_bytes).expect("Error writing the TIFF strip byte counts tag");
-            }
+        let row_length_in_bytes: u32 = r.configs.columns as u32 * total_bytes_per_pixel;
+        for _ in 0..r.configs.rows as u32 {
+            larger_values_data.write_u32(row_length_in_bytes).expect("Error writing the TIFF strip byte counts tag");
         }
     } else {
         ifd_entries.push(Entry::new(
@@ -4131,15 +3998,9 @@ pub fn write_geotiff<'a>(r: &'a mut Raster) -> Result<(), Error> {
                 return Err(Error::new(ErrorKind::InvalidData, "Unknown data type."));
             }
         };
-        if use_compression {
-            for val in strip_byte_counts {
-                larger_values_data.write_u64(val).expect("Error writing the TIFF strip byte counts tag");
-            }
-        } else {
-            let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel;
-            for _ in 0..r.configs.rows as u32 {
-                larger_values_data.write_u64(row_length_in_bytes).expect("Error writing the TIFF strip byte counts tag");
-            }
+        let row_length_in_bytes: u64 = r.configs.columns as u64 * total_bytes_per_pixel;
+        for _ in 0..r.configs.rows as u32 {
+            larger_values_data.write_u64(row_length_in_bytes).expect("Error writing the TIFF strip byte counts tag");
         }
     }
 
@@ -8,6 +8,7 @@ use std::io::BufWriter;
 use std::io::Error;
 use std::io::ErrorKind;
 use std::mem;
+use whitebox_common::errors::ErrorCause;
 
 pub fn read_idrisi(
     file_name: &String,
@@ -116,8 +117,7 @@ pub fn read_idrisi(
             if !vec[1].trim().to_lowercase().contains("binary")
                 || vec[1].trim().to_lowercase().contains("packed")
             {
-                return Err(Error::new(
-                    ErrorKind::InvalidInput,
+                return Err(ErrorCause::UnsupportedFormat.error(
                     "Idrisi ASCII and packed binary files are currently unsupported.",
                 ));
             }
@@ -282,8 +282,7 @@ pub fn write_idrisi<'a>(r: &'a mut Raster) -> Result<(), Error> {
             writer.write_all("data type   : byte\n".as_bytes())?;
         }
         _ => {
-            return Err(Error::new(
-                ErrorKind::NotFound,
+            return Err(ErrorCause::UnsupportedFormat.error(
                 format!(
                     "Raster data type {:?} not supported in this format.",
                     r.configs.data_type
@@ -17,26 +17,58 @@ extern crate num_traits;
 mod arcascii_raster;
 mod arcbinary_raster;
 mod esri_bil;
+mod geopackage_raster;
 pub mod geotiff;
 mod grass_raster;
+mod mapped_raster;
 mod idrisi_raster;
+mod memory_rasters;
+mod netcdf_raster;
+pub mod png;
+mod raster_cache;
+mod raster_provider;
+mod raster_statistics;
 mod saga_raster;
 mod surfer7_raster;
 mod surfer_ascii_raster;
+mod tiled_raster;
+mod virtual_mosaic;
 mod whitebox_raster;
+mod zarr_raster;
+mod zstd_codec;
 
 use self::arcascii_raster::*;
 use self::arcbinary_raster::*;
 use self::esri_bil::*;
+use self::geopackage_raster::*;
 use self::geotiff::*;
 use self::grass_raster::*;
 use self::idrisi_raster::*;
+pub use self::mapped_raster::MappedRaster;
+pub use self::memory_rasters::{
+    clear_memory_rasters, memory_raster_exists, memory_raster_key, remove_memory_raster,
+};
+use self::netcdf_raster::*;
+pub use self::raster_cache::clear_raster_cache;
+use self::raster_cache::{cache_raster, get_cached_raster, uncache_raster};
+use self::raster_provider::raster_provider;
+pub use self::raster_provider::{
+    register_raster_provider, unregister_raster_provider, MemoryRasterProvider, RasterProvider,
+};
+pub use self::raster_statistics::{sidecar_file_name, RasterStatistics, HISTOGRAM_BINS};
 use self::saga_raster::*;
 use self::surfer7_raster::*;
 use self::surfer_ascii_raster::*;
+pub use self::tiled_raster::TiledRaster;
+use self::virtual_mosaic::*;
+pub use self::virtual_mosaic::{MosaicMember, VirtualMosaic, VirtualMosaicReader};
 use self::whitebox_raster::*;
+pub use self::whitebox_raster::WhiteboxTileReader;
+use self::zarr_raster::*;
 use num_traits::cast::AsPrimitive;
+use whitebox_common::spatial_ref_system::{CoordinateReferenceSystem, CrsUnits};
 use whitebox_common::structures::{Array2D, BoundingBox};
+use whitebox_common::errors::ErrorCause;
 use whitebox_common::utils::*;
 use std::cmp::Ordering::Equal;
 use std::default::Default;
@@ -50,9 +82,43 @@ use std::ops::{AddAssign, Index, IndexMut, SubAssign};
 use std::path::Path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 // use rayon::prelude::*;
 
+/// The relative tolerance used when matching values against a nodata value. It
+/// corresponds to the precision of a 32-bit float, which is the precision at which
+/// many raster formats store their nodata values.
+pub const NODATA_TOLERANCE: f64 = f32::EPSILON as f64;
+
+/// Returns `true` if `value` should be treated as nodata given the `nodata` value of a
+/// raster. NaN values are always treated as nodata. Finite values match a finite nodata
+/// value if they are within a relative tolerance (`NODATA_TOLERANCE`) of it, such that
+/// nodata values that have been stored at a lower precision, or rounded, still match.
+///
+/// Tools should use this predicate, or `Raster::is_nodata`, rather than comparing
+/// values with the nodata value directly.
+#[inline]
+pub fn is_nodata(value: f64, nodata: f64) -> bool {
+    if value.is_nan() || value == nodata {
+        return true;
+    }
+    if nodata.is_nan() || nodata.is_infinite() || value.is_infinite() {
+        return false;
+    }
+    (value - nodata).abs() <= nodata.abs() * NODATA_TOLERANCE
+}
+
+/// Returns true if rasters with the file extension `extension` (e.g. 'tif' or 'dep') can be
+/// written.
+pub fn is_writable_raster_extension(extension: &str) -> bool {
+    let extension = extension.trim().trim_start_matches('.');
+    !extension.is_empty()
+        && !matches!(
+            get_raster_type_from_file(format!("raster.{}", extension), "w".to_string()),
+            RasterType::Unknown | RasterType::VirtualMosaic
+        )
+}
+
 /// Raster is a common data structure that abstracts over several raster data formats,
 /// including GeoTIFFs, ArcGIS ASCII and binary rasters, Whitebox rasters, Idrisi
 /// rasters, Saga rasters, and GRASS ASCII rasters.
@@ -137,6 +203,33 @@ impl Raster {
     /// `initialize_using_config` or `initialize_using_file` functions instead.
     pub fn new<'a>(file_name: &'a str, file_mode: &'a str) -> Result<Raster, Error> {
         let fm: String = file_mode.to_lowercase();
+        if fm.contains("r") {
+            if let Some(provider) = raster_provider(file_name) {
+                let (configs, data) = provider.read(file_name)?;
+                return Ok(Raster {
+                    file_name: file_name.to_string(),
+                    file_mode: fm.clone(),
+                    raster_type: get_raster_type_from_file(file_name.to_string(), fm),
+                    configs,
+                    data,
+                });
+            }
+        }
+        if is_url(file_name)
+            && fm.contains("r")
+            && !matches!(
+                get_raster_type_from_file(file_name.to_string(), "w".to_string()),
+                RasterType::GeoTiff | RasterType::VirtualMosaic | RasterType::Zarr
+            )
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Only GeoTIFF, Zarr, and virtual mosaic rasters can be read from URLs and object stores, but {} is not one.",
+                    file_name
+                ),
+            ));
+        }
         let mut r = Raster {
             file_name: file_name.to_string(),
             file_mode: fm.clone(),
@@ -144,53 +237,79 @@ impl Raster {
             ..Default::default()
         };
         if r.file_mode.contains("r") {
-            match get_raster_type_from_file(file_name.to_string(), fm) {
-                RasterType::ArcBinary => {
-                    let _ = read_arcbinary(&r.file_name, &mut r.configs, &mut r.data)?;
-                    // return Ok(r);
-                }
-                RasterType::ArcAscii => {
-                    let _ = read_arcascii(&r.file_name, &mut r.configs, &mut r.data)?;
-                }
-                RasterType::EsriBil => {
-                    let _ = read_esri_bil(&r.file_name, &mut r.configs, &mut r.data)?;
-                }
-                RasterType::GeoTiff => {
-                    let _ = read_geotiff(&r.file_name, &mut r.configs, &mut r.data)?;
-                    r.update_min_max();
-                }
-                RasterType::GrassAscii => {
-                    let _ = read_grass_raster(&r.file_name, &mut r.configs, &mut r.data)?;
-                }
-                RasterType::IdrisiBinary => {
-                    let _ = read_idrisi(&r.file_name, &mut r.configs, &mut r.data)?;
-                }
-                RasterType::SagaBinary => {
-                    let _ = read_saga(&r.file_name, &mut r.configs, &mut r.data)?;
-                }
-                RasterType::Surfer7Binary => {
-                    let _ = read_surfer7(&r.file_name, &mut r.configs, &mut r.data)?;
-                }
-                RasterType::SurferAscii => {
-                    let _ = read_surfer_ascii_raster(&r.file_name, &mut r.configs, &mut r.data)?;
-                }
-                RasterType::Whitebox => {
-                    let _ = read_whitebox(&r.file_name, &mut r.configs, &mut r.data)?;
-                }
-                RasterType::Unknown => {
-                    return Err(Error::new(ErrorKind::Other, "Unrecognized raster type"));
+            let cache_capacity = whitebox_common::configs::get_configs()
+                .map(|c| c.raster_cache_mb)
+                .unwrap_or(0)
+                * 1_048_576;
+            if cache_capacity > 0 {
+                if let Some((configs, data)) = get_cached_raster(file_name) {
+                    r.configs = configs;
+                    r.data = data;
+                    return Ok(r);
                 }
             }
 
-            // The nodata value can't be NaN or Inf because Rust does not handle equality using == with either. 
-            // If the nodata value is either, modify it in memory so that the various tools will work as expected.
-            if r.configs.nodata.is_nan() || r.configs.nodata.is_infinite() {
-                r.configs.nodata = -32768.0;
-                for i in 0..r.data.len() {
-                    if r.data[i].is_nan() || r.data[i].is_infinite() {
-                        r.data[i] = -32768.0;
+            time_io(|| -> Result<(), Error> {
+                match get_raster_type_from_file(file_name.to_string(), fm) {
+                    RasterType::ArcBinary => {
+                        let _ = read_arcbinary(&r.file_name, &mut r.configs, &mut r.data)?;
+                        // return Ok(r);
+                    }
+                    RasterType::ArcAscii => {
+                        let _ = read_arcascii(&r.file_name, &mut r.configs, &mut r.data)?;
+                    }
+                    RasterType::EsriBil => {
+                        let _ = read_esri_bil(&r.file_name, &mut r.configs, &mut r.data)?;
+                    }
+                    RasterType::GeoPackage => {
+                        let _ = read_geopackage(&r.file_name, &mut r.configs, &mut r.data)?;
+                        r.update_min_max();
+                    }
+                    RasterType::GeoTiff => {
+                        let _ = read_geotiff(&r.file_name, &mut r.configs, &mut r.data)?;
+                        r.update_min_max();
+                    }
+                    RasterType::GrassAscii => {
+                        let _ = read_grass_raster(&r.file_name, &mut r.configs, &mut r.data)?;
+                    }
+                    RasterType::IdrisiBinary => {
+                        let _ = read_idrisi(&r.file_name, &mut r.configs, &mut r.data)?;
+                    }
+                    RasterType::NetCdf => {
+                        let _ = read_netcdf(&r.file_name, &mut r.configs, &mut r.data)?;
+                        r.update_min_max();
+                    }
+                    RasterType::SagaBinary => {
+                        let _ = read_saga(&r.file_name, &mut r.configs, &mut r.data)?;
+                    }
+                    RasterType::Surfer7Binary => {
+                        let _ = read_surfer7(&r.file_name, &mut r.configs, &mut r.data)?;
+                    }
+                    RasterType::SurferAscii => {
+                        let _ = read_surfer_ascii_raster(&r.file_name, &mut r.configs, &mut r.data)?;
+                    }
+                    RasterType::VirtualMosaic => {
+                        read_virtual_mosaic(&r.file_name, &mut r.configs, &mut r.data)?;
+                        r.update_min_max();
+                    }
+                    RasterType::Whitebox => {
+                        let _ = read_whitebox(&r.file_name, &mut r.configs, &mut r.data)?;
+                    }
+                    RasterType::Zarr => {
+                        let _ = read_zarr(&r.file_name, &mut r.configs, &mut r.data)?;
+                        r.update_min_max();
+                    }
+                    RasterType::Unknown => {
+                        return Err(ErrorCause::UnsupportedFormat.error("Unrecognized raster type"));
                     }
                 }
+                Ok(())
+            })?;
+
+            r.normalize_nodata();
+
+            if cache_capacity > 0 {
+                cache_raster(file_name, &r.configs, &r.data, cache_capacity);
             }
 
             return Ok(r);
@@ -201,15 +320,104 @@ impl Raster {
         // Err(Error::new(ErrorKind::Other, "Error creating raster"))
     }
 
+    /// Reads a reduced-resolution copy of a raster, e.g. for display, with neither dimension
+    /// much larger than `max_size` cells. The smallest overview (pyramid level) of a GeoTIFF that
+    /// is at least `max_size` cells across is read when there is one, such that only a fraction
+    /// of a large or remote file is decoded; otherwise the raster is read in full and resampled
+    /// by nearest neighbour. The returned raster covers the same extent as the original.
+    pub fn new_overview<'a>(file_name: &'a str, max_size: usize) -> Result<Raster, Error> {
+        let max_size = max_size.max(1);
+        if raster_provider(file_name).is_none()
+            && get_raster_type_from_file(file_name.to_string(), "r".to_string())
+                == RasterType::GeoTiff
+        {
+            let layout = read_tiff_layout(file_name)?;
+            let overview = layout
+                .overviews
+                .iter()
+                .filter(|o| o.1.max(o.2) >= max_size)
+                .min_by_key(|o| o.1 * o.2);
+            if let Some(&(image, _, _)) = overview {
+                let mut r = Raster {
+                    file_name: file_name.to_string(),
+                    file_mode: "r".to_string(),
+                    raster_type: RasterType::GeoTiff,
+                    ..Default::default()
+                };
+                time_io(|| read_geotiff_overview(&r.file_name, &mut r.configs, &mut r.data, image))?;
+                r.update_min_max();
+                r.normalize_nodata();
+                return r.decimate(max_size);
+            }
+        }
+        Raster::new(file_name, "r")?.decimate(max_size)
+    }
+
+    /// Resamples the raster by nearest neighbour, if necessary, such that neither of its
+    /// dimensions is greater than `max_size`, keeping its extent.
+    fn decimate(self, max_size: usize) -> Result<Raster, Error> {
+        let (rows, columns) = (self.configs.rows, self.configs.columns);
+        if rows.max(columns) <= max_size {
+            return Ok(self);
+        }
+        let step = rows.max(columns) as f64 / max_size as f64;
+        let new_rows = ((rows as f64 / step).round() as usize).max(1);
+        let new_columns = ((columns as f64 / step).round() as usize).max(1);
+        let mut configs = self.configs.clone();
+        configs.rows = new_rows;
+        configs.columns = new_columns;
+        configs.resolution_x = (configs.east - configs.west) / new_columns as f64;
+        configs.resolution_y = (configs.north - configs.south) / new_rows as f64;
+        let mut data = Vec::with_capacity(new_rows * new_columns);
+        for row in 0..new_rows {
+            let r = (((row as f64 + 0.5) * rows as f64 / new_rows as f64) as usize).min(rows - 1);
+            for col in 0..new_columns {
+                let c = (((col as f64 + 0.5) * columns as f64 / new_columns as f64) as usize)
+                    .min(columns - 1);
+                data.push(self.data[r * columns + c]);
+            }
+        }
+        let mut r = Raster {
+            file_name: self.file_name,
+            file_mode: self.file_mode,
+            raster_type: self.raster_type,
+            configs: configs,
+            data: data,
+        };
+        r.update_min_max();
+        Ok(r)
+    }
+
+    /// Makes the nodata value and nodata cells of a raster that has just been read safe to
+    /// compare exactly. The nodata value can't be NaN or Inf because Rust does not handle
+    /// equality using == with either; if it is either, it is modified in memory so that the
+    /// various tools will work as expected.
+    fn normalize_nodata(&mut self) {
+        if self.configs.nodata.is_nan() || self.configs.nodata.is_infinite() {
+            self.configs.nodata = -32768.0;
+            for i in 0..self.data.len() {
+                if self.data[i].is_nan() || self.data[i].is_infinite() {
+                    self.data[i] = -32768.0;
+                }
+            }
+        } else {
+            // Snap NaN cells, and cells that differ from the nodata value only by the
+            // rounding introduced by storing it at a lower precision (e.g. -3.4028235e38
+            // vs. -3.40282346639e38), to the exact nodata value, such that tools can
+            // safely use exact comparisons.
+            let nodata = self.configs.nodata;
+            for i in 0..self.data.len() {
+                if self.data[i] != nodata && is_nodata(self.data[i], nodata) {
+                    self.data[i] = nodata;
+                }
+            }
+        }
+    }
+
     /// Creates a new in-memory `Raster` object with grid extent and location
     /// based on specified configurations contained within a `RasterConfigs`.
     pub fn initialize_using_config<'a>(file_name: &'a str, configs: &'a RasterConfigs) -> Raster {
-        let new_file_name = if file_name.contains(".") {
-            file_name.to_string()
-        } else {
-            // likely no extension provided; default to .tif
-            format!("{}.tif", file_name)
-        };
+        let new_file_name = with_default_extension(file_name);
         let mut output = Raster {
             file_name: new_file_name.clone(),
             // configs: configs.clone(),
@@ -260,12 +468,7 @@ impl Raster {
     /// Creates a new in-memory `Raster` object with grid extent and location
     /// based on specified configurations contained within a `RasterConfigs`.
     pub fn initialize_using_array2d<'a, T: AsPrimitive<f64> + Copy + AddAssign + SubAssign>(file_name: &'a str, configs: &'a RasterConfigs, data: Array2D<T>) -> Raster {
-        let new_file_name = if file_name.contains(".") {
-            file_name.to_string()
-        } else {
-            // likely no extension provided; default to .tif
-            format!("{}.tif", file_name)
-        };
+        let new_file_name = with_default_extension(file_name);
         let mut output = Raster {
             file_name: new_file_name.clone(),
             // configs: configs.clone(),
@@ -321,12 +524,7 @@ impl Raster {
     /// Creates a new in-memory `Raster` object with grid extent and location based
     /// on an existing `Raster` contained within `file_name`.
     pub fn initialize_using_file<'a>(file_name: &'a str, input: &'a Raster) -> Raster {
-        let new_file_name = if file_name.contains(".") {
-            file_name.to_string()
-        } else {
-            // likely no extension provided; default to .tif
-            format!("{}.tif", file_name)
-        };
+        let new_file_name = with_default_extension(file_name);
         let mut output = Raster {
             file_name: new_file_name.clone(),
             ..Default::default()
@@ -377,12 +575,7 @@ impl Raster {
         configs: &'a RasterConfigs,
         array: &'a Array2D<T>,
     ) -> Raster {
-        let new_file_name = if file_name.contains(".") {
-            file_name.to_string()
-        } else {
-            // likely no extension provided; default to .tif
-            format!("{}.tif", file_name)
-        };
+        let new_file_name = with_default_extension(file_name);
         let mut output = Raster {
             file_name: new_file_name.clone(),
             ..Default::default()
@@ -450,6 +643,28 @@ impl Raster {
         e.to_string()
     }
 
+    /// Sets the file to which the `Raster` will be written, and the format implied by its
+    /// extension. This allows a raster created in memory, or read from another file, to be
+    /// written by a later call to `write`.
+    pub fn set_file_name(&mut self, file_name: &str) {
+        let new_file_name = with_default_extension(file_name);
+        self.file_name = new_file_name.clone();
+        self.file_mode = "w".to_string();
+        self.raster_type = get_raster_type_from_file(new_file_name, "w".to_string());
+        if (self.raster_type == RasterType::SurferAscii
+            || self.raster_type == RasterType::Surfer7Binary)
+            && self.configs.nodata != 1.71041e38
+        {
+            let old_nodata = self.configs.nodata;
+            for z in self.data.iter_mut() {
+                if *z == old_nodata {
+                    *z = 1.71041e38;
+                }
+            }
+            self.configs.nodata = 1.71041e38;
+        }
+    }
+
     /// Returns the value contained within a grid cell specified
     /// by `row` and `column`.
     pub fn get_value(&self, row: isize, column: isize) -> f64 {
@@ -502,6 +717,12 @@ impl Raster {
         self.configs.nodata
     }
 
+    /// Returns `true` if `value` represents nodata in this raster. See `is_nodata`.
+    #[inline]
+    pub fn is_nodata(&self, value: f64) -> bool {
+        is_nodata(value, self.configs.nodata)
+    }
+
     pub fn set_value(&mut self, row: isize, column: isize, value: f64) {
         if column >= 0 && row >= 0 {
             let c: usize = column as usize;
@@ -519,7 +740,7 @@ impl Raster {
             let r: usize = row as usize;
             if c < self.configs.columns && r < self.configs.rows {
                 let idx = r * self.configs.columns + c;
-                if self.data[idx] != self.configs.nodata {
+                if !is_nodata(self.data[idx], self.configs.nodata) {
                     self.data[idx] -= value;
                 } else {
                     self.data[idx] = value;
@@ -534,7 +755,7 @@ impl Raster {
             let r: usize = row as usize;
             if c < self.configs.columns && r < self.configs.rows {
                 let idx = r * self.configs.columns + c;
-                if self.data[idx] != self.configs.nodata {
+                if !is_nodata(self.data[idx], self.configs.nodata) {
                     self.data[idx] += value;
                 } else {
                     self.data[idx] = value;
@@ -577,7 +798,7 @@ impl Raster {
         }
         for column in 0..self.configs.columns {
             let idx = r * self.configs.columns + column;
-            if self.data[idx] != self.configs.nodata {
+            if !is_nodata(self.data[idx], self.configs.nodata) {
                 self.data[idx] += values[column];
             } else {
                 self.data[idx] = values[column];
@@ -596,7 +817,7 @@ impl Raster {
         }
         for column in 0..self.configs.columns {
             let idx = r * self.configs.columns + column;
-            if self.data[idx] != self.configs.nodata {
+            if !is_nodata(self.data[idx], self.configs.nodata) {
                 self.data[idx] -= values[column];
             } else {
                 self.data[idx] = values[column];
@@ -607,8 +828,7 @@ impl Raster {
     pub fn set_data_from_raster(&mut self, other: &Raster) -> Result<(), Error> {
         if self.configs.rows != other.configs.rows || self.configs.columns != other.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::Other,
+            return Err(ErrorCause::GridMismatch.error(
                 "Rasters must have the same dimensions and extent.",
             ));
         }
@@ -645,7 +865,7 @@ impl Raster {
         for row in 0..self.configs.rows as isize {
             for col in 0..self.configs.columns as isize {
                 z = self.get_value(row, col);
-                if z != self.configs.nodata {
+                if !is_nodata(z, self.configs.nodata) {
                     data.set_value(row, col, z as f32);
                 }
             }
@@ -659,8 +879,7 @@ impl Raster {
     ) -> Result<(), Error> {
         // quality control
         if array.rows * array.columns != self.data.len() as isize {
-            return Err(Error::new(
-                ErrorKind::Other,
+            return Err(ErrorCause::GridMismatch.error(
                 "Rasters must have the same dimensions and extent.",
             ));
         }
@@ -754,7 +973,7 @@ impl Raster {
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         for i in 0..d.len() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     self.configs.display_min = d[i];
@@ -765,7 +984,7 @@ impl Raster {
 
         sum = 0;
         for i in (0..d.len()).rev() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     self.configs.display_max = d[i];
@@ -781,7 +1000,7 @@ impl Raster {
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         for i in 0..d.len() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     self.configs.display_min = d[i];
@@ -797,7 +1016,7 @@ impl Raster {
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         for i in (0..d.len()).rev() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     self.configs.display_max = d[i];
@@ -850,7 +1069,7 @@ impl Raster {
         let mut sum = 0;
         let mut val = 0.0;
         for i in 0..self.num_cells() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     val = d[i];
@@ -860,7 +1079,7 @@ impl Raster {
         }
 
         for i in 0..self.data.len() {
-            if self.data[i] != self.configs.nodata {
+            if !is_nodata(self.data[i], self.configs.nodata) {
                 if self.data[i] < val {
                     self.data[i] = val;
                 }
@@ -877,7 +1096,7 @@ impl Raster {
         let mut sum = 0;
         let mut val = 0.0;
         for i in (0..self.num_cells()).rev() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     val = d[i];
@@ -887,7 +1106,7 @@ impl Raster {
         }
 
         for i in 0..self.data.len() {
-            if self.data[i] != self.configs.nodata {
+            if !is_nodata(self.data[i], self.configs.nodata) {
                 if self.data[i] > val {
                     self.data[i] = val;
                 }
@@ -904,7 +1123,7 @@ impl Raster {
         let mut sum = 0;
         let mut lower_val = 0.0;
         for i in 0..self.num_cells() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     lower_val = d[i];
@@ -916,7 +1135,7 @@ impl Raster {
         let mut upper_val = 0.0;
         let mut sum = 0;
         for i in (0..self.num_cells()).rev() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     upper_val = d[i];
@@ -926,7 +1145,7 @@ impl Raster {
         }
 
         for i in 0..self.data.len() {
-            if self.data[i] != self.configs.nodata {
+            if !is_nodata(self.data[i], self.configs.nodata) {
                 if self.data[i] < lower_val {
                     self.data[i] = lower_val;
                 } else if self.data[i] > upper_val {
@@ -942,20 +1161,20 @@ impl Raster {
     pub fn update_min_max(&mut self) {
         self.configs.minimum = f64::INFINITY;
         self.configs.maximum = f64::NEG_INFINITY;
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let nodata = self.configs.nodata;
         let values = Arc::new(self.data.clone());
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let values = values.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut min_val = f64::INFINITY;
                 let mut max_val = f64::NEG_INFINITY;
                 let mut value: f64;
                 for i in (0..values.len()).filter(|v| v % num_procs == tid) {
                     value = values[i];
-                    if value != nodata {
+                    if !is_nodata(value, nodata) {
                         if value < min_val {
                             min_val = value;
                         }
@@ -1005,16 +1224,16 @@ impl Raster {
         }
         let nodata = self.configs.nodata;
         let values = Arc::new(self.data.clone());
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let num_cells = self.num_cells();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let values = values.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut count = 0usize;
                 for i in (0..num_cells).filter(|r| r % num_procs == tid) {
-                    if values[i] != nodata {
+                    if !is_nodata(values[i], nodata) {
                         count += 1;
                     }
                 }
@@ -1036,17 +1255,17 @@ impl Raster {
         }
         let nodata = self.configs.nodata;
         let values = Arc::new(self.data.clone());
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let num_cells = self.num_cells();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let values = values.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut sum = 0.0f64;
                 let mut count = 0.0f64;
                 for i in (0..num_cells).filter(|r| r % num_procs == tid) {
-                    if values[i] != nodata {
+                    if !is_nodata(values[i], nodata) {
                         sum += values[i];
                         count += 1.0;
                     }
@@ -1074,17 +1293,17 @@ impl Raster {
         let mean = self.calculate_mean();
         let nodata = self.configs.nodata;
         let values = Arc::new(self.data.clone());
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let num_cells = self.num_cells();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let values = values.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut sq_diff_sum = 0.0f64;
                 let mut count = 0.0f64;
                 for i in (0..num_cells).filter(|r| r % num_procs == tid) {
-                    if values[i] != nodata {
+                    if !is_nodata(values[i], nodata) {
                         sq_diff_sum += (values[i] - mean) * (values[i] - mean);
                         count += 1.0;
                     }
@@ -1112,7 +1331,7 @@ impl Raster {
         d.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Equal));
         let mut sum = 0;
         for i in 0..d.len() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     lower_tail = d[i];
@@ -1123,7 +1342,7 @@ impl Raster {
 
         sum = 0;
         for i in (0..d.len()).rev() {
-            if d[i] != self.configs.nodata {
+            if !is_nodata(d[i], self.configs.nodata) {
                 sum += 1;
                 if sum >= t {
                     upper_tail = d[i];
@@ -1142,72 +1361,244 @@ impl Raster {
                 "Cannot write raster that is not created in write mmode ('w').",
             ));
         }
-        match self.raster_type {
-            RasterType::ArcAscii => {
-                let _ = match write_arcascii(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
-            }
-            RasterType::ArcBinary => {
-                let _ = match write_arcbinary(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
+        if let Some(provider) = raster_provider(&self.file_name) {
+            // Rasters written to a provider, including in-memory rasters, keep the full
+            // precision of the data and have no sidecars.
+            return provider.write(&self.file_name, &self.configs, &self.data);
+        }
+        self.apply_output_data_type()?;
+        // The raster is written to a temporary file that replaces the output only once it is
+        // complete, so that an interrupted write never leaves a truncated file behind.
+        let file_name = self.file_name.clone();
+        let result = time_io(|| {
+            write_atomically(&file_name, |temp_file| {
+                self.file_name = temp_file.to_string();
+                self.write_file()
+            })
+        });
+        self.file_name = file_name;
+        uncache_raster(&self.file_name);
+        result?;
+        let configs = whitebox_common::configs::get_configs()?;
+        if configs.cache_statistics
+            && self.configs.photometric_interp != PhotometricInterpretation::RGB
+            && !is_url(&self.file_name)
+        {
+            // A failure to cache the statistics shouldn't cause the write to fail.
+            if let Err(e) = self.calculate_statistics().write_sidecar(&self.file_name) {
+                println!("Warning: the raster statistics sidecar could not be written: {}", e);
             }
-            RasterType::EsriBil => {
-                let _ = match write_esri_bil(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
+        }
+        Ok(())
+    }
+
+    fn write_file(&mut self) -> Result<(), Error> {
+        match self.raster_type {
+            RasterType::ArcAscii => write_arcascii(self),
+            RasterType::ArcBinary => write_arcbinary(self),
+            RasterType::EsriBil => write_esri_bil(self),
+            RasterType::GeoPackage => write_geopackage(self),
+            RasterType::GeoTiff => write_geotiff(self),
+            RasterType::GrassAscii => write_grass_raster(self),
+            RasterType::IdrisiBinary => write_idrisi(self),
+            RasterType::NetCdf => write_netcdf(self),
+            RasterType::SagaBinary => write_saga(self),
+            RasterType::Surfer7Binary => write_surfer7(self),
+            RasterType::SurferAscii => write_surfer_ascii_raster(self),
+            RasterType::VirtualMosaic => write_virtual_mosaic(self),
+            RasterType::Whitebox => write_whitebox(self),
+            RasterType::Zarr => write_zarr(self),
+            RasterType::Unknown => Err(ErrorCause::UnsupportedFormat.error("Unrecognized raster type")),
+        }
+    }
+
+    /// Calculates the statistics (minimum, maximum, mean, standard deviation, and histogram)
+    /// of the raster's valid cells.
+    pub fn calculate_statistics(&self) -> RasterStatistics {
+        RasterStatistics::calculate(&self.data, self.configs.nodata)
+    }
+
+    /// Returns the raster's statistics, reusing those cached in the raster's `.aux.xml`
+    /// sidecar file when the raster was opened for reading and the sidecar is up to date,
+    /// and otherwise calculating them from the data. Cached statistics describe the file
+    /// on disk, and so will not reflect any modifications made to the data in memory.
+    pub fn get_statistics(&self) -> RasterStatistics {
+        if !self.file_mode.contains("w") {
+            if let Ok(stats) = RasterStatistics::read_sidecar(&self.file_name, self.num_cells()) {
+                return stats;
             }
-            RasterType::GeoTiff => {
-                let _ = match write_geotiff(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
+        }
+        self.calculate_statistics()
+    }
+
+    /// Applies the output data type settings (`out_dtype`, `out_scale`, and `out_offset`)
+    /// prior to writing. With the default 'auto' setting, categorical GeoTIFF outputs with
+    /// integer data types are narrowed to the smallest type that holds their values.
+    fn apply_output_data_type(&mut self) -> Result<(), Error> {
+        if self.configs.data_type == DataType::RGB24
+            || self.configs.data_type == DataType::RGB48
+            || self.configs.data_type == DataType::RGBA32
+            || self.configs.photometric_interp == PhotometricInterpretation::RGB
+        {
+            return Ok(());
+        }
+        let configs = whitebox_common::configs::get_configs()?;
+        let out_dtype = configs.out_dtype.trim().to_lowercase();
+        if out_dtype.is_empty() || out_dtype == "auto" {
+            if self.raster_type == RasterType::GeoTiff
+                && self.configs.photometric_interp == PhotometricInterpretation::Categorical
+                && self.configs.data_type.is_integer()
+            {
+                self.narrow_integer_data_type();
             }
-            RasterType::GrassAscii => {
-                let _ = match write_grass_raster(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
+            Ok(())
+        } else if out_dtype == "native" {
+            Ok(())
+        } else {
+            let data_type = out_dtype.parse::<DataType>()?;
+            self.convert_data_type(data_type, configs.out_scale, configs.out_offset)
+        }
+    }
+
+    /// Converts the raster to a new data type, transforming each valid value such that
+    /// stored = (value - offset) / scale, and rounding when the new type is an integer type.
+    /// An error is returned, and the raster left unmodified, if any transformed value falls
+    /// outside of the range of the new data type. The nodata value is retained if the new
+    /// type can represent it and is otherwise replaced by the type's default nodata value.
+    pub fn convert_data_type(
+        &mut self,
+        data_type: DataType,
+        scale: f64,
+        offset: f64,
+    ) -> Result<(), Error> {
+        if scale == 0f64 || !scale.is_finite() || !offset.is_finite() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The output scale must be a finite, non-zero value and the offset must be finite.",
+            ));
+        }
+        let (min_val, max_val) = data_type.value_range();
+        let is_integer = data_type.is_integer();
+        let transform = |z: f64| -> f64 {
+            let v = (z - offset) / scale;
+            if is_integer {
+                v.round()
+            } else {
+                v
             }
-            RasterType::IdrisiBinary => {
-                let _ = match write_idrisi(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
+        };
+        let old_nodata = self.configs.nodata;
+        let new_nodata = if old_nodata >= min_val
+            && old_nodata <= max_val
+            && (!is_integer || old_nodata.fract() == 0f64)
+        {
+            old_nodata
+        } else {
+            data_type.default_nodata()
+        };
+
+        let mut num_out_of_range = 0usize;
+        let mut num_collisions = 0usize;
+        for &z in &self.data {
+            if !is_nodata(z, old_nodata) {
+                let v = transform(z);
+                if v < min_val || v > max_val {
+                    num_out_of_range += 1;
+                } else if v == new_nodata {
+                    num_collisions += 1;
+                }
             }
-            RasterType::SagaBinary => {
-                let _ = match write_saga(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
+        }
+        if num_out_of_range > 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "{} cells in {} have values outside of the range of the {:?} output data type. Use a wider data type or an output scale and offset.",
+                    num_out_of_range, self.file_name, data_type
+                ),
+            ));
+        }
+        if num_collisions > 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "{} cells in {} would be converted to the nodata value ({}) of the {:?} output data type.",
+                    num_collisions, self.file_name, new_nodata, data_type
+                ),
+            ));
+        }
+
+        for z in self.data.iter_mut() {
+            *z = if is_nodata(*z, old_nodata) {
+                new_nodata
+            } else {
+                transform(*z)
+            };
+        }
+        if self.configs.display_min.is_finite() {
+            self.configs.display_min = transform(self.configs.display_min);
+        }
+        if self.configs.display_max.is_finite() {
+            self.configs.display_max = transform(self.configs.display_max);
+        }
+        if scale < 0f64 {
+            std::mem::swap(&mut self.configs.display_min, &mut self.configs.display_max);
+        }
+        self.configs.nodata = new_nodata;
+        self.configs.data_type = data_type;
+        self.update_min_max();
+        if scale != 1f64 || offset != 0f64 {
+            self.add_metadata_entry(format!("scale_factor: {}", scale));
+            self.add_metadata_entry(format!("add_offset: {}", offset));
+        }
+        Ok(())
+    }
+
+    /// Narrows the data type of an integer-valued raster, e.g. a class map, flow pointer, or
+    /// clump ID raster, to the smallest of U8, I16, U16, and I32 that holds the raster's values
+    /// and a nodata value that does not coincide with them. The raster is left unmodified if
+    /// it contains non-integer values or no narrower type is suitable.
+    pub fn narrow_integer_data_type(&mut self) {
+        let nodata = self.configs.nodata;
+        let mut min_val = f64::INFINITY;
+        let mut max_val = f64::NEG_INFINITY;
+        for &z in &self.data {
+            if !is_nodata(z, nodata) {
+                if z.fract() != 0f64 {
+                    return;
+                }
+                if z < min_val {
+                    min_val = z;
+                }
+                if z > max_val {
+                    max_val = z;
+                }
             }
-            RasterType::Surfer7Binary => {
-                let _ = match write_surfer7(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
+        }
+        if min_val > max_val {
+            // there are no valid cells
+            return;
+        }
+        let current_size = self.configs.data_type.get_data_size();
+        for data_type in [DataType::U8, DataType::I16, DataType::U16, DataType::I32] {
+            if data_type.get_data_size() >= current_size {
+                break;
             }
-            RasterType::SurferAscii => {
-                let _ = match write_surfer_ascii_raster(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
+            let (lo, hi) = data_type.value_range();
+            if min_val < lo || max_val > hi {
+                continue;
             }
-            RasterType::Whitebox => {
-                let _ = match write_whitebox(self) {
-                    Ok(_) => (),
-                    Err(e) => println!("error while writing: {:?}", e),
-                };
+            let nodata_fits = nodata.fract() == 0f64 && nodata >= lo && nodata <= hi;
+            let default_nodata = data_type.default_nodata();
+            if !nodata_fits && default_nodata >= min_val && default_nodata <= max_val {
+                continue;
             }
-            RasterType::Unknown => {
-                return Err(Error::new(ErrorKind::Other, "Unrecognized raster type"));
+            // The data fit within the type and a non-coincident nodata value is available,
+            // so the conversion cannot fail.
+            if self.convert_data_type(data_type, 1f64, 0f64).is_ok() {
+                return;
             }
         }
-        Ok(())
     }
 
     pub fn add_metadata_entry(&mut self, value: String) {
@@ -1230,6 +1621,78 @@ impl Raster {
         )
     }
 
+    /// Returns `true` if this raster shares the grid of `configs`, i.e. it has the same number of rows
+    /// and columns and its edges coincide to within a small fraction of a grid cell.
+    pub fn is_aligned_with(&self, configs: &RasterConfigs) -> bool {
+        let tol_x = 0.001 * self.configs.resolution_x;
+        let tol_y = 0.001 * self.configs.resolution_y;
+        self.configs.rows == configs.rows
+            && self.configs.columns == configs.columns
+            && (self.configs.north - configs.north).abs() <= tol_y
+            && (self.configs.south - configs.south).abs() <= tol_y
+            && (self.configs.east - configs.east).abs() <= tol_x
+            && (self.configs.west - configs.west).abs() <= tol_x
+    }
+
+    /// Resamples the raster onto the grid described by `configs`, cropping the data or padding them
+    /// with NoData as needed. Values are taken from the nearest cell unless `bilinear` is `true`, in
+    /// which case they are bilinearly interpolated from the four nearest cells, with NoData cells
+    /// excluded from the weighting. The returned raster retains the data type, NoData value, and
+    /// display properties of this raster.
+    pub fn resample_to_grid(&self, configs: &RasterConfigs, bilinear: bool) -> Raster {
+        let mut output = Raster::initialize_using_config(&self.file_name, configs);
+        output.file_mode = self.file_mode.clone();
+        output.configs.nodata = self.configs.nodata;
+        output.configs.data_type = self.configs.data_type;
+        output.configs.photometric_interp = self.configs.photometric_interp;
+        output.configs.palette = self.configs.palette.clone();
+        output.configs.z_units = self.configs.z_units.clone();
+        output.reinitialize_values(self.configs.nodata);
+        let nodata = self.configs.nodata;
+        let res_x = self.configs.resolution_x;
+        let res_y = self.configs.resolution_y;
+        for row in 0..output.configs.rows as isize {
+            let y = output.get_y_from_row(row);
+            for col in 0..output.configs.columns as isize {
+                let x = output.get_x_from_column(col);
+                let (r, c) = (self.get_row_from_y(y), self.get_column_from_x(x));
+                if r < 0 || c < 0 || r >= self.configs.rows as isize || c >= self.configs.columns as isize {
+                    continue;
+                }
+                if !bilinear {
+                    output.set_value(row, col, self.get_value(r, c));
+                    continue;
+                }
+                // fractional position relative to the cell centres
+                let fr = (self.configs.north - y) / res_y - 0.5;
+                let fc = (x - self.configs.west) / res_x - 0.5;
+                let (r0, c0) = (fr.floor() as isize, fc.floor() as isize);
+                let (dr, dc) = (fr - r0 as f64, fc - c0 as f64);
+                let mut sum = 0f64;
+                let mut weights = 0f64;
+                for (rn, cn, w) in [
+                    (r0, c0, (1f64 - dr) * (1f64 - dc)),
+                    (r0, c0 + 1, (1f64 - dr) * dc),
+                    (r0 + 1, c0, dr * (1f64 - dc)),
+                    (r0 + 1, c0 + 1, dr * dc),
+                ] {
+                    if rn < 0 || cn < 0 || rn >= self.configs.rows as isize || cn >= self.configs.columns as isize {
+                        continue;
+                    }
+                    let z = self.get_value(rn, cn);
+                    if w > 0f64 && !is_nodata(z, nodata) {
+                        sum += w * z;
+                        weights += w;
+                    }
+                }
+                if weights > 0f64 {
+                    output.set_value(row, col, sum / weights);
+                }
+            }
+        }
+        output
+    }
+
     pub fn is_in_geographic_coordinates(&self) -> bool {
         if self.configs.west < -180f64
             || self.configs.east > 180f64
@@ -1335,19 +1798,72 @@ impl Default for RasterConfigs {
     }
 }
 
+impl RasterConfigs {
+    /// Returns the coordinate reference system of the raster, from its EPSG code, its WKT
+    /// definition (or a projection that is a WKT definition), and its xy_units.
+    pub fn crs(&self) -> CoordinateReferenceSystem {
+        let wkt = if self.coordinate_ref_system_wkt.contains('[') {
+            &self.coordinate_ref_system_wkt
+        } else {
+            &self.projection
+        };
+        CoordinateReferenceSystem::new(self.epsg_code, wkt, &self.xy_units)
+    }
+
+    /// Sets the coordinate reference system of the raster. The GeoTIFF geokeys, which describe
+    /// the previous CRS, are cleared if the EPSG code changes.
+    pub fn set_crs(&mut self, crs: &CoordinateReferenceSystem) {
+        if crs.epsg_code != self.epsg_code {
+            self.geo_key_directory.clear();
+            self.geo_double_params.clear();
+            self.geo_ascii_params.clear();
+        }
+        self.epsg_code = crs.epsg_code;
+        self.coordinate_ref_system_wkt = if crs.wkt.is_empty() {
+            "not specified".to_string()
+        } else {
+            crs.wkt.clone()
+        };
+        if !crs.wkt.is_empty() {
+            self.projection = crs.wkt.clone();
+        }
+        if crs.units != CrsUnits::Unknown {
+            self.xy_units = crs.units.name().to_string();
+        }
+    }
+
+    /// Returns a warning if the coordinate reference systems of this raster (named `name`) and
+    /// another input (named `other_name`) are known and differ, which tools with several inputs
+    /// issue in addition to checking that the inputs share a grid.
+    pub fn crs_mismatch_warning(&self, name: &str, other: &RasterConfigs, other_name: &str) -> Option<String> {
+        let (crs, other_crs) = (self.crs(), other.crs());
+        match crs.matches(&other_crs) {
+            Some(false) => Some(format!(
+                "Warning: {} ({}) and {} ({}) have different coordinate reference systems. The inputs are assumed to be in the same system, which may give incorrect results.",
+                name, crs, other_name, other_crs
+            )),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RasterType {
     Unknown,
     ArcAscii,
     ArcBinary,
     EsriBil,
+    GeoPackage,
     GeoTiff,
     GrassAscii,
     IdrisiBinary,
+    NetCdf,
     SagaBinary,
     Surfer7Binary,
     SurferAscii,
+    VirtualMosaic,
     Whitebox,
+    Zarr,
 }
 
 impl Default for RasterType {
@@ -1356,7 +1872,32 @@ impl Default for RasterType {
     }
 }
 
+/// Returns whether a metadata string, e.g. the WKT of a raster, has been given a value.
+fn is_specified(s: &str) -> bool {
+    !s.trim().is_empty() && !s.trim().eq_ignore_ascii_case("not specified")
+}
+
+/// Returns a raster file name with the extension of the default output format (the
+/// `default_raster_format` setting) appended, if it likely has no extension.
+fn with_default_extension(file_name: &str) -> String {
+    if file_name.contains(".") {
+        return file_name.to_string();
+    }
+    let extension = match whitebox_common::configs::get_configs() {
+        Ok(configs) if !configs.default_raster_format.trim().is_empty() => {
+            configs.default_raster_format.trim().trim_start_matches('.').to_lowercase()
+        }
+        _ => "tif".to_string(),
+    };
+    format!("{}.{}", file_name, extension)
+}
+
 fn get_raster_type_from_file(file_name: String, file_mode: String) -> RasterType {
+    if raster_provider(&file_name).is_some() {
+        // Rasters of a provider, e.g. in-memory rasters, have no format of their own, and need
+        // no extension.
+        return RasterType::GeoTiff;
+    }
     // get the file extension
     let extension: String = match Path::new(&file_name).extension().unwrap().to_str() {
         Some(n) => n.to_string().to_lowercase(),
@@ -1378,10 +1919,18 @@ fn get_raster_type_from_file(file_name: String, file_mode: String) -> RasterType
         return RasterType::GeoTiff;
     } else if extension == "bil" {
         return RasterType::EsriBil;
+    } else if extension == "gpkg" {
+        return RasterType::GeoPackage;
     } else if extension == "flt" {
         return RasterType::ArcBinary;
     } else if extension == "rdc" || extension == "rst" {
         return RasterType::IdrisiBinary;
+    } else if extension == "nc" {
+        return RasterType::NetCdf;
+    } else if extension == "zarr" {
+        return RasterType::Zarr;
+    } else if extension == "wbvrt" {
+        return RasterType::VirtualMosaic;
     } else if extension == "sdat" || extension == "sgrd" {
         return RasterType::SagaBinary;
     } else if extension == "grd" {
@@ -1522,6 +2071,61 @@ impl DataType {
             _ => false
         }
     }
+
+    /// Returns the minimum and maximum values that can be represented by the data type.
+    pub fn value_range(&self) -> (f64, f64) {
+        match *self {
+            DataType::F64 => (f64::MIN, f64::MAX),
+            DataType::F32 => (f32::MIN as f64, f32::MAX as f64),
+            DataType::I64 => (i64::MIN as f64, i64::MAX as f64),
+            DataType::I32 => (i32::MIN as f64, i32::MAX as f64),
+            DataType::I16 => (i16::MIN as f64, i16::MAX as f64),
+            DataType::I8 => (i8::MIN as f64, i8::MAX as f64),
+            DataType::U64 => (0f64, u64::MAX as f64),
+            DataType::U32 => (0f64, u32::MAX as f64),
+            DataType::U16 => (0f64, u16::MAX as f64),
+            DataType::U8 | DataType::RGB24 | DataType::RGBA32 => (0f64, u8::MAX as f64),
+            DataType::RGB48 => (0f64, u16::MAX as f64),
+            DataType::Unknown => (f64::MIN, f64::MAX),
+        }
+    }
+
+    /// Returns the nodata value conventionally used with the data type, i.e. the maximum
+    /// value of unsigned integer types, the minimum value of signed integer types, and
+    /// -32768 for floating-point types.
+    pub fn default_nodata(&self) -> f64 {
+        if self.is_unsigned_integer() {
+            self.value_range().1
+        } else if self.is_signed_integer() {
+            self.value_range().0
+        } else {
+            -32768f64
+        }
+    }
+}
+
+impl std::str::FromStr for DataType {
+    type Err = Error;
+
+    /// Parses a data type name, e.g. 'u8', 'i16', 'u16', 'i32', 'f32', or 'f64'.
+    fn from_str(s: &str) -> Result<DataType, Error> {
+        match s.trim().to_lowercase().as_str() {
+            "f64" | "float64" | "double" => Ok(DataType::F64),
+            "f32" | "float32" | "float" => Ok(DataType::F32),
+            "i64" | "int64" => Ok(DataType::I64),
+            "i32" | "int32" => Ok(DataType::I32),
+            "i16" | "int16" => Ok(DataType::I16),
+            "i8" | "int8" => Ok(DataType::I8),
+            "u64" | "uint64" => Ok(DataType::U64),
+            "u32" | "uint32" => Ok(DataType::U32),
+            "u16" | "uint16" => Ok(DataType::U16),
+            "u8" | "uint8" | "byte" => Ok(DataType::U8),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unrecognized raster data type '{}'.", s),
+            )),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
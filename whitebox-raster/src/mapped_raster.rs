@@ -0,0 +1,193 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: A MappedRaster provides read-only access to an uncompressed, single-band GeoTIFF by
+memory-mapping the file and decoding cell values on request, rather than decoding the whole
+image into a Vec<f64> when the raster is opened. The operating system pages the file in and
+out as needed, so a mapped input adds little to a tool's peak memory use, which is useful for
+secondary inputs (e.g. the back-link raster of CostAllocation) that are read alongside a full
+in-memory raster. Both striped and tiled files are supported, in either byte order. Compressed
+or multi-band files, or files using a predictor, cannot be mapped and should be read with
+Raster::new instead (see `can_map`).
+
+Unlike Raster::new, opening a MappedRaster does not scan the data, so the display minimum and
+maximum of the configs are left as stored in the file.
+
+wasm32 builds have no memory mapping, and there the file is read into memory when the raster is
+opened, with its cells still decoded on request.
+*/
+
+use super::*;
+use crate::geotiff::{read_geotiff_block_layout, GeoTiffBlockLayout};
+#[cfg(not(target_arch = "wasm32"))]
+use memmap2::Mmap;
+
+#[cfg(target_arch = "wasm32")]
+type Mmap = Vec<u8>;
+
+/// A read-only raster whose cells are decoded directly from a memory-mapped GeoTIFF.
+///
+/// Example:
+///
+/// ```no_run
+/// # use whitebox_raster::MappedRaster;
+/// # fn main() -> std::io::Result<()> {
+/// # let (input_file, row, col) = ("dem.tif", 0, 0);
+/// if MappedRaster::can_map(input_file) {
+///     let input = MappedRaster::open(input_file)?;
+///     let z = input.get_value(row, col);
+/// #   let _ = z;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct MappedRaster {
+    pub file_name: String,
+    pub configs: RasterConfigs,
+    layout: GeoTiffBlockLayout,
+    bytes_per_cell: usize,
+    mmap: Mmap,
+    // non-finite values are reported as the nodata value, as in Raster::new
+    replace_non_finite: bool,
+}
+
+impl MappedRaster {
+    /// Returns true if `file_name` is an uncompressed, single-band GeoTIFF that can be opened
+    /// as a `MappedRaster`.
+    pub fn can_map(file_name: &str) -> bool {
+        if is_memory_dataset(file_name)
+            || is_url(file_name)
+            || Path::new(file_name).extension().is_none()
+        {
+            return false;
+        }
+        if get_raster_type_from_file(file_name.to_string(), "r".to_string()) != RasterType::GeoTiff
+        {
+            return false;
+        }
+        let mut configs = RasterConfigs {
+            ..Default::default()
+        };
+        read_geotiff_block_layout(file_name, &mut configs).is_ok()
+    }
+
+    /// Opens an uncompressed, single-band GeoTIFF for read-only access.
+    pub fn open(file_name: &str) -> Result<MappedRaster, Error> {
+        if get_raster_type_from_file(file_name.to_string(), "r".to_string()) != RasterType::GeoTiff
+            || is_memory_dataset(file_name)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Only GeoTIFF files can be memory-mapped, but {} is not one.", file_name),
+            ));
+        }
+        let mut configs = RasterConfigs {
+            ..Default::default()
+        };
+        let layout = read_geotiff_block_layout(file_name, &mut configs)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let mmap = {
+            let file = File::open(file_name)?;
+            // Safety: the map is read-only and the tools never modify their input files while
+            // running. If another process truncates the file, cells beyond its end read as nodata.
+            unsafe { Mmap::map(&file)? }
+        };
+        #[cfg(target_arch = "wasm32")]
+        let mmap = std::fs::read(file_name)?;
+        let replace_non_finite = configs.nodata.is_nan() || configs.nodata.is_infinite();
+        if replace_non_finite {
+            configs.nodata = -32768.0;
+        }
+        let bytes_per_cell = layout.bits_per_sample as usize / 8;
+        Ok(MappedRaster {
+            file_name: file_name.to_string(),
+            configs,
+            layout,
+            bytes_per_cell,
+            mmap,
+            replace_non_finite,
+        })
+    }
+
+    /// Returns the value of the cell at (`row`, `column`), or nodata if the cell lies outside
+    /// of the grid or within a sparse block.
+    pub fn get_value(&self, row: isize, column: isize) -> f64 {
+        let nodata = self.configs.nodata;
+        if row < 0
+            || column < 0
+            || row >= self.configs.rows as isize
+            || column >= self.configs.columns as isize
+        {
+            return nodata;
+        }
+        let (r, c) = (row as usize, column as usize);
+        let layout = &self.layout;
+        let block = (r / layout.block_height) * layout.blocks_across + c / layout.block_width;
+        if block >= layout.block_offsets.len() || layout.block_counts[block] == 0 {
+            return nodata;
+        }
+        let cell = (r % layout.block_height) * layout.block_width + c % layout.block_width;
+        let start = layout.block_offsets[block] as usize + cell * self.bytes_per_cell;
+        let end = start + self.bytes_per_cell;
+        if end > self.mmap.len() {
+            return nodata;
+        }
+        let v = self.decode(&self.mmap[start..end]);
+        if (self.replace_non_finite && v.is_infinite()) || is_nodata(v, nodata) {
+            return nodata;
+        }
+        v
+    }
+
+    /// Returns the values of one row of the grid.
+    pub fn get_row_data(&self, row: isize) -> Vec<f64> {
+        (0..self.configs.columns as isize)
+            .map(|col| self.get_value(row, col))
+            .collect()
+    }
+
+    pub fn is_nodata(&self, value: f64) -> bool {
+        is_nodata(value, self.configs.nodata)
+    }
+
+    pub fn num_cells(&self) -> usize {
+        self.configs.rows * self.configs.columns
+    }
+
+    pub fn get_short_filename(&self) -> String {
+        let path = Path::new(&self.file_name);
+        let file_name = path.file_stem().unwrap();
+        let f = file_name.to_str().unwrap();
+        f.to_string()
+    }
+
+    fn decode(&self, b: &[u8]) -> f64 {
+        macro_rules! from_bytes {
+            ($t:ty, $n:expr) => {{
+                let mut a = [0u8; $n];
+                a.copy_from_slice(b);
+                if self.configs.endian == Endianness::LittleEndian {
+                    <$t>::from_le_bytes(a) as f64
+                } else {
+                    <$t>::from_be_bytes(a) as f64
+                }
+            }};
+        }
+        match (self.layout.sample_format, self.layout.bits_per_sample) {
+            (1, 8) => b[0] as f64,
+            (1, 16) => from_bytes!(u16, 2),
+            (1, 32) => from_bytes!(u32, 4),
+            (1, _) => from_bytes!(u64, 8),
+            (2, 8) => b[0] as i8 as f64,
+            (2, 16) => from_bytes!(i16, 2),
+            (2, 32) => from_bytes!(i32, 4),
+            (2, _) => from_bytes!(i64, 8),
+            (_, 32) => from_bytes!(f32, 4),
+            _ => from_bytes!(f64, 8),
+        }
+    }
+}
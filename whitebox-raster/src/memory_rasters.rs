@@ -0,0 +1,86 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: In-memory rasters are named with a `memory:` prefix (e.g. `memory:breached`) and are
+held by the process instead of being written to disk, so that the intermediate outputs of a
+multi-step workflow can be passed between tools without the cost of encoding, writing, reading
+and decoding files. A raster written to such a name is stored here, at full precision, and a
+raster opened for reading with such a name is a copy of the stored one. Any file extension is
+ignored, i.e. `memory:breached` and `memory:breached.tif` are the same dataset, because tools
+add a default extension to output names that lack one. The datasets remain in memory until they
+are removed or the process ends. The store is the built-in `MemoryRasterProvider` (see
+raster_provider.rs).
+*/
+
+use super::RasterConfigs;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use whitebox_common::utils::is_memory_dataset;
+
+type MemoryRaster = (RasterConfigs, Vec<f64>);
+
+static MEMORY_RASTERS: Mutex<Option<HashMap<String, MemoryRaster>>> = Mutex::new(None);
+
+/// Returns the key of an in-memory raster name, i.e. the name without the `memory:` prefix and
+/// any file extension. Names without the prefix, e.g. those of a scheme for which a
+/// `MemoryRasterProvider` is registered, keep the rest of their name.
+pub fn memory_raster_key(file_name: &str) -> String {
+    let name = file_name.trim();
+    let name = if is_memory_dataset(name) {
+        &name[7..]
+    } else {
+        name
+    };
+    match name.rfind('.') {
+        Some(i) if i > 0 && !name[i..].contains(['/', '\\']) => name[..i].to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// Returns a copy of an in-memory raster's configurations and data, if it exists.
+pub(crate) fn get_memory_raster(file_name: &str) -> Option<MemoryRaster> {
+    let rasters = MEMORY_RASTERS.lock().ok()?;
+    rasters
+        .as_ref()?
+        .get(&memory_raster_key(file_name))
+        .map(|(configs, data)| (configs.clone(), data.clone()))
+}
+
+/// Stores a raster in memory, replacing any existing raster of the same name.
+pub(crate) fn store_memory_raster(file_name: &str, configs: &RasterConfigs, data: &[f64]) {
+    if let Ok(mut rasters) = MEMORY_RASTERS.lock() {
+        rasters
+            .get_or_insert_with(HashMap::new)
+            .insert(memory_raster_key(file_name), (configs.clone(), data.to_vec()));
+    }
+}
+
+/// Returns true if an in-memory raster named `file_name`, e.g. `memory:breached`, exists.
+pub fn memory_raster_exists(file_name: &str) -> bool {
+    match MEMORY_RASTERS.lock() {
+        Ok(rasters) => rasters
+            .as_ref()
+            .is_some_and(|r| r.contains_key(&memory_raster_key(file_name))),
+        Err(_) => false,
+    }
+}
+
+/// Removes an in-memory raster, freeing its memory.
+pub fn remove_memory_raster(file_name: &str) {
+    if let Ok(mut rasters) = MEMORY_RASTERS.lock() {
+        if let Some(r) = rasters.as_mut() {
+            r.remove(&memory_raster_key(file_name));
+        }
+    }
+}
+
+/// Removes all of the in-memory rasters.
+pub fn clear_memory_rasters() {
+    if let Ok(mut rasters) = MEMORY_RASTERS.lock() {
+        *rasters = None;
+    }
+}
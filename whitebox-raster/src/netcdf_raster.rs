@@ -0,0 +1,1409 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Reading and writing rasters stored as NetCDF (.nc) files in the classic binary formats
+(CDF-1, the 64-bit offset CDF-2, and the 64-bit data CDF-5), following the CF conventions for
+georeferencing, which is the form in which climate reanalysis and ocean model grids are usually
+distributed. NetCDF-4 files, which are HDF5 files, are not supported and must first be converted,
+e.g. with 'nccopy -k classic in.nc out.nc'.
+
+Reading uses the first data variable with two or more dimensions, the last two of which are
+taken to be the y and x dimensions. The remaining, leading dimensions (e.g. time and depth) form
+the bands of the variable. The time step that is read can be selected by date, or time
+coordinate value, with the --time flag, in which case the --band flag selects among the bands
+of the remaining leading dimensions, and otherwise the --band flag (1-based) selects among all
+of the bands, ordered with the last leading dimension varying fastest. Packed values are unpacked
+using the scale_factor and add_offset attributes, and cells equal to the _FillValue (or the
+netCDF default fill value) or a missing_value are read as nodata. The grid is georeferenced from
+the coordinate variables of the x and y dimensions, or failing that, from the GDAL GeoTransform
+attribute of the grid mapping variable, and grids stored from south to north are flipped.
+
+Writing creates a CDF-1 file (or a CDF-2 file, when the data don't fit within 2 GB) holding a
+single two-dimensional variable, with x/y (or lon/lat) coordinate variables and a 'crs' grid
+mapping variable that holds the WKT of the raster, as crs_wkt and spatial_ref attributes, and its
+GDAL-style GeoTransform. The classic formats have no unsigned or 64-bit integer types, and so
+rasters of these types are written as doubles, and unsigned 8-bit rasters as shorts.
+*/
+
+use super::*;
+use chrono::{Datelike, NaiveDate};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Read, Seek, SeekFrom, Write};
+use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+
+const NC_DIMENSION: u32 = 0x0A;
+const NC_VARIABLE: u32 = 0x0B;
+const NC_ATTRIBUTE: u32 = 0x0C;
+const STREAMING: u32 = 0xFFFF_FFFF;
+
+const NC_BYTE: u32 = 1;
+const NC_CHAR: u32 = 2;
+const NC_SHORT: u32 = 3;
+const NC_INT: u32 = 4;
+const NC_FLOAT: u32 = 5;
+const NC_DOUBLE: u32 = 6;
+const NC_UBYTE: u32 = 7;
+const NC_USHORT: u32 = 8;
+const NC_UINT: u32 = 9;
+const NC_INT64: u32 = 10;
+const NC_UINT64: u32 = 11;
+
+/// The netCDF default fill values, used when a variable has no _FillValue attribute.
+const NC_FILL_SHORT: f64 = -32767.0;
+const NC_FILL_INT: f64 = -2147483647.0;
+const NC_FILL_FLOAT: f32 = 9.969_21e36;
+const NC_FILL_DOUBLE: f64 = 9.969_209_968_386_869e36;
+
+const HDF5_SIGNATURE: [u8; 8] = [0x89, b'H', b'D', b'F', 0x0D, 0x0A, 0x1A, 0x0A];
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+fn type_size(nc_type: u32) -> Result<usize, Error> {
+    match nc_type {
+        NC_BYTE | NC_CHAR | NC_UBYTE => Ok(1),
+        NC_SHORT | NC_USHORT => Ok(2),
+        NC_INT | NC_FLOAT | NC_UINT => Ok(4),
+        NC_DOUBLE | NC_INT64 | NC_UINT64 => Ok(8),
+        _ => Err(invalid(&format!(
+            "Unrecognized netCDF data type ({}).",
+            nc_type
+        ))),
+    }
+}
+
+/// Returns the (big-endian) value of type `nc_type` at `index` within `bytes`.
+fn decode_value(bytes: &[u8], index: usize, nc_type: u32) -> f64 {
+    let b = |size: usize| &bytes[index * size..(index + 1) * size];
+    match nc_type {
+        NC_BYTE => bytes[index] as i8 as f64,
+        NC_CHAR | NC_UBYTE => bytes[index] as f64,
+        NC_SHORT => i16::from_be_bytes([b(2)[0], b(2)[1]]) as f64,
+        NC_USHORT => u16::from_be_bytes([b(2)[0], b(2)[1]]) as f64,
+        NC_INT => i32::from_be_bytes(b(4).try_into().unwrap()) as f64,
+        NC_UINT => u32::from_be_bytes(b(4).try_into().unwrap()) as f64,
+        NC_FLOAT => f32::from_be_bytes(b(4).try_into().unwrap()) as f64,
+        NC_DOUBLE => f64::from_be_bytes(b(8).try_into().unwrap()),
+        NC_INT64 => i64::from_be_bytes(b(8).try_into().unwrap()) as f64,
+        NC_UINT64 => u64::from_be_bytes(b(8).try_into().unwrap()) as f64,
+        _ => f64::NAN,
+    }
+}
+
+fn encode_value(bytes: &mut Vec<u8>, value: f64, nc_type: u32) {
+    match nc_type {
+        NC_BYTE => bytes.push(value as i8 as u8),
+        NC_SHORT => bytes.extend_from_slice(&(value as i16).to_be_bytes()),
+        NC_INT => bytes.extend_from_slice(&(value as i32).to_be_bytes()),
+        NC_FLOAT => bytes.extend_from_slice(&(value as f32).to_be_bytes()),
+        _ => bytes.extend_from_slice(&value.to_be_bytes()),
+    }
+}
+
+fn padding(len: usize) -> usize {
+    (4 - len % 4) % 4
+}
+
+#[derive(Clone)]
+enum AttrValue {
+    Text(String),
+    Numbers(Vec<f64>),
+}
+
+#[derive(Clone)]
+struct NcAttribute {
+    name: String,
+    nc_type: u32,
+    value: AttrValue,
+}
+
+struct NcDimension {
+    name: String,
+    /// The length of the dimension, which for the record dimension is the number of records.
+    len: usize,
+}
+
+struct NcVariable {
+    name: String,
+    dims: Vec<usize>,
+    attributes: Vec<NcAttribute>,
+    nc_type: u32,
+    begin: u64,
+}
+
+fn find_attribute<'a>(attributes: &'a [NcAttribute], name: &str) -> Option<&'a AttrValue> {
+    attributes
+        .iter()
+        .find(|a| a.name.eq_ignore_ascii_case(name))
+        .map(|a| &a.value)
+}
+
+impl NcVariable {
+    fn attribute(&self, name: &str) -> Option<&AttrValue> {
+        find_attribute(&self.attributes, name)
+    }
+
+    fn text_attribute(&self, name: &str) -> Option<String> {
+        match self.attribute(name) {
+            Some(AttrValue::Text(s)) => Some(s.trim_end_matches('\0').trim().to_string()),
+            _ => None,
+        }
+    }
+
+    fn number_attribute(&self, name: &str) -> Option<f64> {
+        match self.attribute(name) {
+            Some(AttrValue::Numbers(v)) if !v.is_empty() => Some(v[0]),
+            Some(AttrValue::Text(s)) => s.trim().parse::<f64>().ok(),
+            _ => None,
+        }
+    }
+
+    fn number_attributes(&self, name: &str) -> Vec<f64> {
+        match self.attribute(name) {
+            Some(AttrValue::Numbers(v)) => v.clone(),
+            _ => vec![],
+        }
+    }
+}
+
+struct NcHeader {
+    version: u8,
+    num_records: usize,
+    dims: Vec<NcDimension>,
+    attributes: Vec<NcAttribute>,
+    vars: Vec<NcVariable>,
+    record_dim: Option<usize>,
+}
+
+/// Reads the elements of a classic-format header, whose counts and lengths are 64-bit values
+/// in CDF-5 files and 32-bit values otherwise.
+struct HeaderReader<R: Read> {
+    reader: R,
+    version: u8,
+}
+
+impl<R: Read> HeaderReader<R> {
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        let mut buf = [0u8; 4];
+        self.reader.read_exact(&mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Error> {
+        let mut buf = [0u8; 8];
+        self.reader.read_exact(&mut buf)?;
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    fn read_count(&mut self) -> Result<usize, Error> {
+        if self.version == 5 {
+            Ok(self.read_u64()? as usize)
+        } else {
+            Ok(self.read_u32()? as usize)
+        }
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, Error> {
+        let mut buf = vec![0u8; len + padding(len)];
+        self.reader.read_exact(&mut buf)?;
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    fn read_name(&mut self) -> Result<String, Error> {
+        let len = self.read_count()?;
+        Ok(String::from_utf8_lossy(&self.read_bytes(len)?).to_string())
+    }
+
+    /// Reads the tag and number of elements of a list, which are both zero for an absent list.
+    fn read_list_header(&mut self, tag: u32) -> Result<usize, Error> {
+        let list_tag = self.read_u32()?;
+        let n = self.read_count()?;
+        if list_tag != tag && !(list_tag == 0 && n == 0) {
+            return Err(invalid(
+                "The netCDF header does not appear to be formatted correctly.",
+            ));
+        }
+        Ok(n)
+    }
+
+    fn read_attributes(&mut self) -> Result<Vec<NcAttribute>, Error> {
+        let n = self.read_list_header(NC_ATTRIBUTE)?;
+        let mut attributes = Vec::with_capacity(n);
+        for _ in 0..n {
+            let name = self.read_name()?;
+            let nc_type = self.read_u32()?;
+            let num_values = self.read_count()?;
+            let bytes = self.read_bytes(num_values * type_size(nc_type)?)?;
+            let value = if nc_type == NC_CHAR {
+                AttrValue::Text(String::from_utf8_lossy(&bytes).to_string())
+            } else {
+                AttrValue::Numbers(
+                    (0..num_values)
+                        .map(|i| decode_value(&bytes, i, nc_type))
+                        .collect(),
+                )
+            };
+            attributes.push(NcAttribute {
+                name,
+                nc_type,
+                value,
+            });
+        }
+        Ok(attributes)
+    }
+}
+
+fn read_header(file_name: &str) -> Result<(NcHeader, File), Error> {
+    let mut f = File::open(file_name)?;
+    let mut magic = [0u8; 8];
+    let num_read = f.read(&mut magic)?;
+    if num_read == 8 && magic == HDF5_SIGNATURE {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "{} is a NetCDF-4 (HDF5) file, which is not supported. Convert it to the classic format first, e.g. with 'nccopy -k classic'.",
+                file_name
+            ),
+        ));
+    }
+    if num_read < 4 || &magic[0..3] != b"CDF" || ![1u8, 2, 5].contains(&magic[3]) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("{} does not appear to be a netCDF file.", file_name),
+        ));
+    }
+    let version = magic[3];
+    f.seek(SeekFrom::Start(4))?;
+    let mut hr = HeaderReader {
+        reader: BufReader::new(f),
+        version,
+    };
+
+    let num_records = if version == 5 {
+        hr.read_u64()? as usize
+    } else {
+        match hr.read_u32()? {
+            STREAMING => 0,
+            n => n as usize,
+        }
+    };
+
+    let num_dims = hr.read_list_header(NC_DIMENSION)?;
+    let mut dims = Vec::with_capacity(num_dims);
+    let mut record_dim = None;
+    for i in 0..num_dims {
+        let name = hr.read_name()?;
+        let mut len = hr.read_count()?;
+        if len == 0 {
+            record_dim = Some(i);
+            len = num_records;
+        }
+        dims.push(NcDimension { name, len });
+    }
+
+    let attributes = hr.read_attributes()?;
+
+    let num_vars = hr.read_list_header(NC_VARIABLE)?;
+    let mut vars = Vec::with_capacity(num_vars);
+    for _ in 0..num_vars {
+        let name = hr.read_name()?;
+        let rank = hr.read_count()?;
+        let mut var_dims = Vec::with_capacity(rank);
+        for _ in 0..rank {
+            let d = hr.read_count()?;
+            if d >= dims.len() {
+                return Err(invalid(
+                    "The netCDF header refers to a non-existent dimension.",
+                ));
+            }
+            var_dims.push(d);
+        }
+        let var_attributes = hr.read_attributes()?;
+        let nc_type = hr.read_u32()?;
+        type_size(nc_type)?;
+        let _vsize = hr.read_count()?;
+        let begin = if version == 1 {
+            hr.read_u32()? as u64
+        } else {
+            hr.read_u64()?
+        };
+        vars.push(NcVariable {
+            name,
+            dims: var_dims,
+            attributes: var_attributes,
+            nc_type,
+            begin,
+        });
+    }
+
+    let f = hr.reader.into_inner();
+    Ok((
+        NcHeader {
+            version,
+            num_records,
+            dims,
+            attributes,
+            vars,
+            record_dim,
+        },
+        f,
+    ))
+}
+
+impl NcHeader {
+    fn is_record_var(&self, var: &NcVariable) -> bool {
+        !var.dims.is_empty() && Some(var.dims[0]) == self.record_dim
+    }
+
+    /// The size, in bytes, of the data of a variable within each record, or in total for a
+    /// variable that isn't a record variable, before padding.
+    fn slab_size(&self, var: &NcVariable) -> usize {
+        let skip = if self.is_record_var(var) { 1 } else { 0 };
+        var.dims[skip..]
+            .iter()
+            .map(|&d| self.dims[d].len)
+            .product::<usize>()
+            * type_size(var.nc_type).unwrap_or(1)
+    }
+
+    /// The size of each record, i.e. the sum of the padded slabs of the record variables. A
+    /// single record variable is stored without padding.
+    fn record_size(&self) -> usize {
+        let record_vars: Vec<&NcVariable> =
+            self.vars.iter().filter(|v| self.is_record_var(v)).collect();
+        if record_vars.len() == 1 {
+            return self.slab_size(record_vars[0]);
+        }
+        record_vars
+            .iter()
+            .map(|v| {
+                let s = self.slab_size(v);
+                s + padding(s)
+            })
+            .sum()
+    }
+
+    fn coordinate_var(&self, dim: usize) -> Option<&NcVariable> {
+        self.vars
+            .iter()
+            .find(|v| v.dims.len() == 1 && v.dims[0] == dim && v.name == self.dims[dim].name)
+    }
+
+    /// Reads `count` values of a variable, starting at the flattened index `start`, which must
+    /// not span records.
+    fn read_values(
+        &self,
+        f: &mut File,
+        var: &NcVariable,
+        start: usize,
+        count: usize,
+    ) -> Result<Vec<u8>, Error> {
+        let size = type_size(var.nc_type)?;
+        let offset = if self.is_record_var(var) {
+            let per_record = self.slab_size(var) / size;
+            let record = start.checked_div(per_record).unwrap_or(0);
+            var.begin
+                + (record * self.record_size()) as u64
+                + ((start - record * per_record) * size) as u64
+        } else {
+            var.begin + (start * size) as u64
+        };
+        let mut bytes = vec![0u8; count * size];
+        f.seek(SeekFrom::Start(offset))?;
+        f.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn read_all_values(&self, f: &mut File, var: &NcVariable) -> Result<Vec<f64>, Error> {
+        let count: usize = var.dims.iter().map(|&d| self.dims[d].len).product();
+        // the values of record variables are read one record at a time
+        let per_record = if self.is_record_var(var) {
+            self.slab_size(var) / type_size(var.nc_type)?
+        } else {
+            count
+        };
+        let mut values = Vec::with_capacity(count);
+        let mut start = 0;
+        while start < count && per_record > 0 {
+            let bytes = self.read_values(f, var, start, per_record)?;
+            values.extend((0..per_record).map(|i| decode_value(&bytes, i, var.nc_type)));
+            start += per_record;
+        }
+        Ok(values)
+    }
+}
+
+/// Returns whether a dimension is a spatial one, judging by the name, axis, standard name, or
+/// units of its coordinate variable.
+fn is_spatial_dim(header: &NcHeader, dim: usize) -> bool {
+    let name = header.dims[dim].name.to_lowercase();
+    if [
+        "x",
+        "y",
+        "lon",
+        "lat",
+        "longitude",
+        "latitude",
+        "rlon",
+        "rlat",
+        "easting",
+        "northing",
+    ]
+    .contains(&name.as_str())
+    {
+        return true;
+    }
+    match header.coordinate_var(dim) {
+        Some(v) => {
+            let axis = v.text_attribute("axis").unwrap_or_default().to_lowercase();
+            let standard_name = v
+                .text_attribute("standard_name")
+                .unwrap_or_default()
+                .to_lowercase();
+            let units = v.text_attribute("units").unwrap_or_default().to_lowercase();
+            axis == "x"
+                || axis == "y"
+                || standard_name.contains("longitude")
+                || standard_name.contains("latitude")
+                || standard_name.starts_with("projection_")
+                || units.starts_with("degrees_east")
+                || units.starts_with("degrees_north")
+        }
+        None => false,
+    }
+}
+
+/// Selects the variable read from a file: the first variable with two or more dimensions that
+/// isn't a coordinate or bounds variable, preferring those whose last two dimensions are
+/// spatial.
+fn select_variable(header: &NcHeader) -> Option<usize> {
+    let bounds: Vec<String> = header
+        .vars
+        .iter()
+        .filter_map(|v| v.text_attribute("bounds"))
+        .collect();
+    let candidates: Vec<usize> = (0..header.vars.len())
+        .filter(|&i| {
+            let v = &header.vars[i];
+            v.dims.len() >= 2 && v.nc_type != NC_CHAR && !bounds.contains(&v.name)
+        })
+        .collect();
+    candidates
+        .iter()
+        .copied()
+        .find(|&i| {
+            let d = &header.vars[i].dims;
+            is_spatial_dim(header, d[d.len() - 1]) && is_spatial_dim(header, d[d.len() - 2])
+        })
+        .or_else(|| candidates.first().copied())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Calendar {
+    Standard,
+    NoLeap,
+    AllLeap,
+    Day360,
+}
+
+impl Calendar {
+    fn from_attribute(s: &str) -> Result<Calendar, Error> {
+        match s.to_lowercase().as_str() {
+            "" | "standard" | "gregorian" | "proleptic_gregorian" => Ok(Calendar::Standard),
+            "noleap" | "365_day" => Ok(Calendar::NoLeap),
+            "all_leap" | "366_day" => Ok(Calendar::AllLeap),
+            "360_day" => Ok(Calendar::Day360),
+            _ => Err(invalid(&format!(
+                "The netCDF calendar '{}' is not supported.",
+                s
+            ))),
+        }
+    }
+
+    fn month_lengths(&self) -> [u32; 12] {
+        match self {
+            Calendar::AllLeap => [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31],
+            Calendar::Day360 => [30; 12],
+            _ => [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31],
+        }
+    }
+
+    /// Returns the number of the day of a date, counted from an arbitrary epoch.
+    fn day_number(&self, year: i64, month: u32, day: u32) -> Option<i64> {
+        if *self == Calendar::Standard {
+            return NaiveDate::from_ymd_opt(year as i32, month, day)
+                .map(|d| d.num_days_from_ce() as i64);
+        }
+        if !(1..=12).contains(&month) || day < 1 || day > self.month_lengths()[month as usize - 1] {
+            return None;
+        }
+        let lengths = self.month_lengths();
+        let year_length: u32 = lengths.iter().sum();
+        let preceding: u32 = lengths[..month as usize - 1].iter().sum();
+        Some(year * year_length as i64 + (preceding + day - 1) as i64)
+    }
+
+    fn date(&self, day_number: i64) -> (i64, u32, u32) {
+        if *self == Calendar::Standard {
+            return match NaiveDate::from_num_days_from_ce_opt(day_number as i32) {
+                Some(d) => (d.year() as i64, d.month(), d.day()),
+                None => (0, 1, 1),
+            };
+        }
+        let lengths = self.month_lengths();
+        let year_length = lengths.iter().sum::<u32>() as i64;
+        let year = day_number.div_euclid(year_length);
+        let mut remainder = day_number.rem_euclid(year_length) as u32;
+        let mut month = 1;
+        for len in lengths.iter() {
+            if remainder < *len {
+                break;
+            }
+            remainder -= len;
+            month += 1;
+        }
+        (year, month, remainder + 1)
+    }
+}
+
+/// A date and time within a calendar, as a day number and the seconds into the day.
+#[derive(Clone, Copy)]
+struct DateTime {
+    day: i64,
+    seconds: f64,
+}
+
+/// Parses dates such as '2020-07-01', '2020-07-01 12:00:00', and '2020-07-01T12:00:00Z'. The
+/// seconds into the day are only returned when a time is given. Time zones are ignored.
+fn parse_date_time(s: &str) -> Option<(i64, u32, u32, Option<f64>)> {
+    let s = s.trim();
+    let (date, time) = match s.find(['T', ' ']) {
+        Some(i) => (&s[..i], Some(s[i + 1..].trim())),
+        None => (s, None),
+    };
+    let negative = date.starts_with('-');
+    let parts: Vec<&str> = date.trim_start_matches('-').split('-').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let mut year = parts[0].parse::<i64>().ok()?;
+    if negative {
+        year = -year;
+    }
+    let month = parts[1].parse::<u32>().ok()?;
+    let day = parts[2].parse::<u32>().ok()?;
+    let seconds = match time {
+        Some(t) if !t.is_empty() => {
+            let t = t
+                .trim_end_matches(['Z', 'z'])
+                .split([' ', '+'])
+                .next()
+                .unwrap_or("");
+            let hms: Vec<&str> = t.split(':').collect();
+            let mut seconds = 0f64;
+            for (i, v) in hms.iter().enumerate().take(3) {
+                seconds += v.trim().parse::<f64>().ok()? * [3600f64, 60f64, 1f64][i];
+            }
+            Some(seconds)
+        }
+        _ => None,
+    };
+    Some((year, month, day, seconds))
+}
+
+/// The CF time units of a time coordinate variable, e.g. 'days since 1900-01-01 00:00:00'.
+struct TimeUnits {
+    unit_seconds: f64,
+    reference: DateTime,
+    calendar: Calendar,
+}
+
+impl TimeUnits {
+    fn parse(units: &str, calendar: Calendar) -> Option<TimeUnits> {
+        let lower = units.to_lowercase();
+        let i = lower.find(" since ")?;
+        let unit_seconds = match lower[..i].trim() {
+            "seconds" | "second" | "secs" | "sec" | "s" => 1f64,
+            "minutes" | "minute" | "mins" | "min" => 60f64,
+            "hours" | "hour" | "hrs" | "hr" | "h" => 3600f64,
+            "days" | "day" | "d" => 86400f64,
+            _ => return None,
+        };
+        let (year, month, day, seconds) = parse_date_time(&units[i + 7..])?;
+        Some(TimeUnits {
+            unit_seconds,
+            reference: DateTime {
+                day: calendar.day_number(year, month, day)?,
+                seconds: seconds.unwrap_or(0f64),
+            },
+            calendar,
+        })
+    }
+
+    fn date_time(&self, value: f64) -> DateTime {
+        let total = self.reference.seconds + value * self.unit_seconds;
+        let days = (total / 86400f64).floor();
+        DateTime {
+            day: self.reference.day + days as i64,
+            seconds: total - days * 86400f64,
+        }
+    }
+
+    fn format(&self, dt: DateTime) -> String {
+        let (year, month, day) = self.calendar.date(dt.day);
+        let seconds = dt.seconds.round() as i64;
+        if seconds == 0 {
+            format!("{:04}-{:02}-{:02}", year, month, day)
+        } else {
+            format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                year,
+                month,
+                day,
+                seconds / 3600,
+                (seconds % 3600) / 60,
+                seconds % 60
+            )
+        }
+    }
+}
+
+fn is_time_dim(header: &NcHeader, dim: usize) -> bool {
+    let name = header.dims[dim].name.to_lowercase();
+    if name == "time" || name == "t" {
+        return true;
+    }
+    match header.coordinate_var(dim) {
+        Some(v) => {
+            v.text_attribute("axis")
+                .unwrap_or_default()
+                .eq_ignore_ascii_case("t")
+                || v.text_attribute("standard_name").unwrap_or_default() == "time"
+                || v.text_attribute("units")
+                    .unwrap_or_default()
+                    .to_lowercase()
+                    .contains(" since ")
+        }
+        None => false,
+    }
+}
+
+/// Returns the index of the time step selected by `time`, which is either a date or a value of
+/// the time coordinate, along with a description of the step.
+fn select_time_step(
+    header: &NcHeader,
+    f: &mut File,
+    dim: usize,
+    time: &str,
+) -> Result<(usize, String), Error> {
+    let coord = header.coordinate_var(dim).ok_or_else(|| {
+        invalid("The time dimension of the netCDF variable has no coordinate variable.")
+    })?;
+    let values = header.read_all_values(f, coord)?;
+    if values.is_empty() {
+        return Err(invalid("The netCDF variable has no time steps."));
+    }
+    let calendar = Calendar::from_attribute(&coord.text_attribute("calendar").unwrap_or_default())?;
+    let units = TimeUnits::parse(&coord.text_attribute("units").unwrap_or_default(), calendar);
+
+    if let Some((year, month, day, seconds)) = parse_date_time(time) {
+        let units = units.ok_or_else(|| {
+            invalid("The units of the netCDF time coordinate cannot be interpreted as dates.")
+        })?;
+        let target = calendar.day_number(year, month, day).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "{} is not a valid date in the {} calendar.",
+                    time,
+                    calendar_name(calendar)
+                ),
+            )
+        })?;
+        for (i, v) in values.iter().enumerate() {
+            let dt = units.date_time(*v);
+            let matches = match seconds {
+                Some(s) => ((dt.day - target) as f64 * 86400f64 + dt.seconds - s).abs() < 0.5,
+                None => dt.day == target,
+            };
+            if matches {
+                return Ok((i, units.format(dt)));
+            }
+        }
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "No time step of the netCDF variable matches {}; its {} time steps run from {} to {}.",
+                time,
+                values.len(),
+                units.format(units.date_time(values[0])),
+                units.format(units.date_time(values[values.len() - 1]))
+            ),
+        ));
+    }
+
+    let target = time.trim().parse::<f64>().map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "The time '{}' is neither a date (e.g. 2020-07-01) nor a time coordinate value.",
+                time
+            ),
+        )
+    })?;
+    let tolerance = 1e-9 * target.abs().max(1f64);
+    match values.iter().position(|v| (v - target).abs() <= tolerance) {
+        Some(i) => Ok((
+            i,
+            match &units {
+                Some(u) => u.format(u.date_time(values[i])),
+                None => format!("{}", values[i]),
+            },
+        )),
+        None => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "No time step of the netCDF variable has the time coordinate value {}.",
+                time
+            ),
+        )),
+    }
+}
+
+fn calendar_name(calendar: Calendar) -> &'static str {
+    match calendar {
+        Calendar::Standard => "standard",
+        Calendar::NoLeap => "noleap",
+        Calendar::AllLeap => "all_leap",
+        Calendar::Day360 => "360_day",
+    }
+}
+
+/// Returns the centre coordinates of the cells along a spatial dimension, along with its units.
+fn read_cell_centres(
+    header: &NcHeader,
+    f: &mut File,
+    dim: usize,
+) -> Result<Option<(Vec<f64>, String)>, Error> {
+    match header.coordinate_var(dim) {
+        Some(v) => Ok(Some((
+            header.read_all_values(f, v)?,
+            v.text_attribute("units").unwrap_or_default(),
+        ))),
+        None => Ok(None),
+    }
+}
+
+pub fn read_netcdf(
+    file_name: &String,
+    configs: &mut RasterConfigs,
+    data: &mut Vec<f64>,
+) -> Result<(), Error> {
+    let (header, mut f) = read_header(file_name)?;
+    let var_index = select_variable(&header).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "The netCDF file {} does not contain a gridded variable with two or more dimensions.",
+                file_name
+            ),
+        )
+    })?;
+    let var = &header.vars[var_index];
+    let rank = var.dims.len();
+    let (y_dim, x_dim) = (var.dims[rank - 2], var.dims[rank - 1]);
+    let rows = header.dims[y_dim].len;
+    let columns = header.dims[x_dim].len;
+    if rows == 0 || columns == 0 {
+        return Err(invalid("The netCDF variable is empty."));
+    }
+    configs.rows = rows;
+    configs.columns = columns;
+
+    // Select the band, i.e. the position along each of the leading dimensions.
+    let wb_configs = whitebox_common::configs::get_configs()?;
+    let leading: Vec<usize> = var.dims[..rank - 2].to_vec();
+    let mut position = vec![0usize; leading.len()];
+    let mut selection = vec![];
+    let mut free: Vec<usize> = (0..leading.len()).collect();
+    if !wb_configs.netcdf_time.trim().is_empty() {
+        let t = (0..leading.len())
+            .find(|&i| is_time_dim(&header, leading[i]))
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "A time was specified, but the netCDF variable {} has no time dimension.",
+                        var.name
+                    ),
+                )
+            })?;
+        let (step, description) =
+            select_time_step(&header, &mut f, leading[t], &wb_configs.netcdf_time)?;
+        position[t] = step;
+        free.retain(|&i| i != t);
+        selection.push(format!("time: {}", description));
+    }
+    let num_bands: usize = free.iter().map(|&i| header.dims[leading[i]].len).product();
+    let band = wb_configs.netcdf_band.max(1);
+    if band > num_bands {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Band {} was specified, but the netCDF variable {} has {} band(s).",
+                band, var.name, num_bands
+            ),
+        ));
+    }
+    let mut remainder = band - 1;
+    for &i in free.iter().rev() {
+        let len = header.dims[leading[i]].len;
+        position[i] = remainder % len;
+        remainder /= len;
+    }
+    if num_bands > 1 {
+        selection.push(format!("band: {} of {}", band, num_bands));
+    }
+    let mut start = 0usize;
+    for (i, &d) in leading.iter().enumerate() {
+        start = start * header.dims[d].len + position[i];
+    }
+    start *= rows * columns;
+
+    // Read and unpack the values.
+    let num_cells = rows * columns;
+    let bytes = header.read_values(&mut f, var, start, num_cells)?;
+    let scale = var.number_attribute("scale_factor").unwrap_or(1f64);
+    let offset = var.number_attribute("add_offset").unwrap_or(0f64);
+    let is_packed = scale != 1f64 || offset != 0f64;
+    let explicit_fill = var.number_attribute("_FillValue");
+    let fill = explicit_fill.unwrap_or(match var.nc_type {
+        NC_SHORT => NC_FILL_SHORT,
+        NC_INT => NC_FILL_INT,
+        NC_FLOAT => NC_FILL_FLOAT as f64,
+        NC_DOUBLE => NC_FILL_DOUBLE,
+        _ => f64::NAN,
+    });
+    let missing = var.number_attributes("missing_value");
+    configs.nodata = match explicit_fill {
+        Some(v) if !is_packed && v.is_finite() => v,
+        _ => -32768f64,
+    };
+    let nodata = configs.nodata;
+    let is_missing = |v: f64| {
+        v.is_nan()
+            || v == fill
+            || (var.nc_type == NC_FLOAT && v as f32 == fill as f32)
+            || missing.contains(&v)
+    };
+
+    // grids stored from south to north are flipped
+    let x_coords = read_cell_centres(&header, &mut f, x_dim)?;
+    let y_coords = read_cell_centres(&header, &mut f, y_dim)?;
+    let flip = match &y_coords {
+        Some((y, _)) => y.len() > 1 && y[y.len() - 1] > y[0],
+        None => false,
+    };
+    data.clear();
+    data.resize(num_cells, nodata);
+    for row in 0..rows {
+        let source_row = if flip { rows - 1 - row } else { row };
+        for column in 0..columns {
+            let v = decode_value(&bytes, source_row * columns + column, var.nc_type);
+            if !is_missing(v) {
+                data[row * columns + column] = v * scale + offset;
+            }
+        }
+    }
+
+    configs.data_type = if is_packed {
+        DataType::F32
+    } else {
+        match var.nc_type {
+            NC_BYTE => DataType::I8,
+            NC_SHORT => DataType::I16,
+            NC_INT => DataType::I32,
+            NC_FLOAT => DataType::F32,
+            NC_UBYTE => DataType::U8,
+            NC_USHORT => DataType::U16,
+            NC_UINT => DataType::U32,
+            NC_INT64 => DataType::I64,
+            NC_UINT64 => DataType::U64,
+            _ => DataType::F64,
+        }
+    };
+    let (min_value, max_value) = configs.data_type.value_range();
+    if configs.data_type.is_integer()
+        && (nodata.fract() != 0f64 || nodata < min_value || nodata > max_value)
+    {
+        // the nodata value can't be represented by the type of the variable
+        configs.data_type = DataType::F32;
+    }
+
+    // Georeference the grid.
+    let grid_mapping = var
+        .text_attribute("grid_mapping")
+        .and_then(|name| header.vars.iter().find(|v| v.name == name));
+    let geo_transform: Vec<f64> = grid_mapping
+        .and_then(|gm| gm.text_attribute("GeoTransform"))
+        .map(|s| {
+            s.split_whitespace()
+                .filter_map(|v| v.parse::<f64>().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    match (&x_coords, &y_coords) {
+        (Some((x, x_units)), Some((y, _))) => {
+            configs.resolution_x = if columns > 1 {
+                ((x[columns - 1] - x[0]) / (columns - 1) as f64).abs()
+            } else if geo_transform.len() == 6 {
+                geo_transform[1].abs()
+            } else {
+                1f64
+            };
+            configs.resolution_y = if rows > 1 {
+                ((y[rows - 1] - y[0]) / (rows - 1) as f64).abs()
+            } else if geo_transform.len() == 6 {
+                geo_transform[5].abs()
+            } else {
+                configs.resolution_x
+            };
+            let (x_min, y_max) = (
+                x.iter().cloned().fold(f64::INFINITY, f64::min),
+                y.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            );
+            configs.west = x_min - configs.resolution_x / 2f64;
+            configs.north = y_max + configs.resolution_y / 2f64;
+            let x_units = x_units.to_lowercase();
+            if x_units.starts_with("degree") {
+                configs.xy_units = "degrees".to_string();
+            } else if x_units == "m" || x_units.starts_with("met") {
+                configs.xy_units = "metres".to_string();
+            } else if is_specified(&x_units) {
+                configs.xy_units = x_units;
+            }
+        }
+        _ if geo_transform.len() == 6 => {
+            configs.west = geo_transform[0];
+            configs.resolution_x = geo_transform[1].abs();
+            configs.north = geo_transform[3];
+            configs.resolution_y = geo_transform[5].abs();
+            if geo_transform[5] > 0f64 {
+                // a bottom-up GeoTransform, whose origin is the south-west corner
+                configs.north = geo_transform[3] + rows as f64 * geo_transform[5];
+            }
+        }
+        _ => {
+            configs.west = 0f64;
+            configs.north = rows as f64;
+            configs.resolution_x = 1f64;
+            configs.resolution_y = 1f64;
+        }
+    }
+    configs.east = configs.west + columns as f64 * configs.resolution_x;
+    configs.south = configs.north - rows as f64 * configs.resolution_y;
+    configs.pixel_is_area = true;
+
+    let mut wkt = String::new();
+    if let Some(gm) = grid_mapping {
+        wkt = gm
+            .text_attribute("crs_wkt")
+            .or_else(|| gm.text_attribute("spatial_ref"))
+            .unwrap_or_default();
+        if let Some(epsg) = gm.text_attribute("epsg_code").or_else(|| {
+            gm.number_attribute("epsg_code")
+                .map(|v| format!("{}", v as u16))
+        }) {
+            configs.epsg_code = epsg
+                .to_uppercase()
+                .trim_start_matches("EPSG:")
+                .trim()
+                .parse::<u16>()
+                .unwrap_or(0);
+        }
+        if configs.epsg_code == 0
+            && !is_specified(&wkt)
+            && gm.text_attribute("grid_mapping_name").unwrap_or_default() == "latitude_longitude"
+        {
+            configs.epsg_code = 4326;
+        }
+    } else if configs.xy_units == "degrees" && !is_specified(&wkt) {
+        configs.epsg_code = 4326;
+    }
+    if is_specified(&wkt) {
+        configs.coordinate_ref_system_wkt = wkt;
+    } else if configs.epsg_code > 0 {
+        let wkt = esri_wkt_from_epsg(configs.epsg_code);
+        if !wkt.starts_with("Unknown") {
+            configs.coordinate_ref_system_wkt = wkt;
+        }
+    }
+    if configs.epsg_code == 4326 {
+        configs.xy_units = "degrees".to_string();
+    }
+
+    configs.title = var.text_attribute("long_name").unwrap_or(var.name.clone());
+    if let Some(units) = var.text_attribute("units") {
+        configs.z_units = units;
+    }
+    configs
+        .metadata
+        .push(format!("NetCDF variable: {}", var.name));
+    for s in selection {
+        configs.metadata.push(format!("NetCDF {}", s));
+    }
+    if let Some(AttrValue::Text(title)) = find_attribute(&header.attributes, "title") {
+        configs
+            .metadata
+            .push(format!("NetCDF title: {}", title.trim()));
+    }
+    configs.metadata.push(format!(
+        "NetCDF format: CDF-{}{}",
+        header.version,
+        if header.num_records > 0 && header.is_record_var(var) {
+            format!(", {} record(s)", header.num_records)
+        } else {
+            String::new()
+        }
+    ));
+
+    Ok(())
+}
+
+/// A variable of a file that is being written, whose data are `size` bytes long.
+struct OutputVariable {
+    name: String,
+    dims: Vec<usize>,
+    attributes: Vec<NcAttribute>,
+    nc_type: u32,
+    size: usize,
+}
+
+fn text_attribute(name: &str, value: &str) -> NcAttribute {
+    NcAttribute {
+        name: name.to_string(),
+        nc_type: NC_CHAR,
+        value: AttrValue::Text(value.to_string()),
+    }
+}
+
+fn number_attribute(name: &str, nc_type: u32, value: f64) -> NcAttribute {
+    NcAttribute {
+        name: name.to_string(),
+        nc_type,
+        value: AttrValue::Numbers(vec![value]),
+    }
+}
+
+/// Writes the elements of a classic-format header, with 32-bit counts and lengths.
+fn put_u32(bytes: &mut Vec<u8>, v: u32) {
+    bytes.extend_from_slice(&v.to_be_bytes());
+}
+
+fn put_name(bytes: &mut Vec<u8>, name: &str) {
+    put_u32(bytes, name.len() as u32);
+    bytes.extend_from_slice(name.as_bytes());
+    bytes.resize(bytes.len() + padding(name.len()), 0);
+}
+
+fn put_attributes(bytes: &mut Vec<u8>, attributes: &[NcAttribute]) {
+    if attributes.is_empty() {
+        put_u32(bytes, 0);
+        put_u32(bytes, 0);
+        return;
+    }
+    put_u32(bytes, NC_ATTRIBUTE);
+    put_u32(bytes, attributes.len() as u32);
+    for a in attributes {
+        put_name(bytes, &a.name);
+        put_u32(bytes, a.nc_type);
+        let start = bytes.len();
+        match &a.value {
+            AttrValue::Text(s) => {
+                put_u32(bytes, s.len() as u32);
+                bytes.extend_from_slice(s.as_bytes());
+            }
+            AttrValue::Numbers(values) => {
+                put_u32(bytes, values.len() as u32);
+                for v in values {
+                    encode_value(bytes, *v, a.nc_type);
+                }
+            }
+        }
+        let len = bytes.len() - start - 4;
+        bytes.resize(bytes.len() + padding(len), 0);
+    }
+}
+
+fn encode_header(
+    version: u8,
+    dims: &[(&str, usize)],
+    attributes: &[NcAttribute],
+    vars: &[OutputVariable],
+    begins: &[u64],
+) -> Vec<u8> {
+    let mut bytes = vec![b'C', b'D', b'F', version];
+    put_u32(&mut bytes, 0); // the number of records
+    put_u32(&mut bytes, NC_DIMENSION);
+    put_u32(&mut bytes, dims.len() as u32);
+    for (name, len) in dims {
+        put_name(&mut bytes, name);
+        put_u32(&mut bytes, *len as u32);
+    }
+    put_attributes(&mut bytes, attributes);
+    put_u32(&mut bytes, NC_VARIABLE);
+    put_u32(&mut bytes, vars.len() as u32);
+    for (v, begin) in vars.iter().zip(begins) {
+        put_name(&mut bytes, &v.name);
+        put_u32(&mut bytes, v.dims.len() as u32);
+        for d in &v.dims {
+            put_u32(&mut bytes, *d as u32);
+        }
+        put_attributes(&mut bytes, &v.attributes);
+        put_u32(&mut bytes, v.nc_type);
+        // the vsize of variables larger than 4 GB is given as 2^32 - 1
+        put_u32(
+            &mut bytes,
+            (v.size + padding(v.size)).min(u32::MAX as usize) as u32,
+        );
+        if version == 1 {
+            put_u32(&mut bytes, *begin as u32);
+        } else {
+            bytes.extend_from_slice(&begin.to_be_bytes());
+        }
+    }
+    bytes
+}
+
+/// Returns the netCDF type in which the values of a raster are stored, which must be able to
+/// represent its nodata value.
+fn output_type(configs: &RasterConfigs) -> u32 {
+    let (nc_type, range) = match configs.data_type {
+        DataType::F32 => return NC_FLOAT,
+        DataType::I32 | DataType::U16 => (NC_INT, (i32::MIN as f64, i32::MAX as f64)),
+        DataType::I16 | DataType::U8 => (NC_SHORT, (i16::MIN as f64, i16::MAX as f64)),
+        DataType::I8 => (NC_BYTE, (i8::MIN as f64, i8::MAX as f64)),
+        _ => return NC_DOUBLE,
+    };
+    let nodata = configs.nodata;
+    if nodata.fract() == 0f64 && nodata >= range.0 && nodata <= range.1 {
+        nc_type
+    } else {
+        NC_DOUBLE
+    }
+}
+
+/// Returns a variable name, i.e. one made of letters, digits and underscores, that starts with
+/// a letter.
+fn variable_name(short_name: &str) -> String {
+    let mut name: String = short_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name = format!("v_{}", name);
+    }
+    name
+}
+
+pub fn write_netcdf(r: &mut Raster) -> Result<(), Error> {
+    if r.configs.data_type == DataType::RGB24
+        || r.configs.data_type == DataType::RGB48
+        || r.configs.data_type == DataType::RGBA32
+        || r.configs.photometric_interp == PhotometricInterpretation::RGB
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Colour composite rasters cannot be written as netCDF files.",
+        ));
+    }
+
+    // figure out the minimum and maximum values
+    for val in &r.data {
+        let v = *val;
+        if v != r.configs.nodata {
+            if v < r.configs.minimum {
+                r.configs.minimum = v;
+            }
+            if v > r.configs.maximum {
+                r.configs.maximum = v;
+            }
+        }
+    }
+
+    let (rows, columns) = (r.configs.rows, r.configs.columns);
+    let geographic = r.is_in_geographic_coordinates();
+    let (x_name, y_name) = if geographic {
+        ("lon", "lat")
+    } else {
+        ("x", "y")
+    };
+    let data_type = output_type(&r.configs);
+    let data_name = variable_name(&r.get_short_filename());
+
+    let mut x_attributes = vec![];
+    let mut y_attributes = vec![];
+    if geographic {
+        x_attributes.push(text_attribute("standard_name", "longitude"));
+        x_attributes.push(text_attribute("long_name", "longitude"));
+        x_attributes.push(text_attribute("units", "degrees_east"));
+        y_attributes.push(text_attribute("standard_name", "latitude"));
+        y_attributes.push(text_attribute("long_name", "latitude"));
+        y_attributes.push(text_attribute("units", "degrees_north"));
+    } else {
+        x_attributes.push(text_attribute("standard_name", "projection_x_coordinate"));
+        x_attributes.push(text_attribute("long_name", "x coordinate of projection"));
+        y_attributes.push(text_attribute("standard_name", "projection_y_coordinate"));
+        y_attributes.push(text_attribute("long_name", "y coordinate of projection"));
+        let xy_units = r.configs.xy_units.to_lowercase();
+        let units = if xy_units.starts_with("met") || xy_units == "m" {
+            "m".to_string()
+        } else if xy_units.starts_with("f") {
+            "ft".to_string()
+        } else {
+            String::new()
+        };
+        if !units.is_empty() {
+            x_attributes.push(text_attribute("units", &units));
+            y_attributes.push(text_attribute("units", &units));
+        }
+    }
+    x_attributes.push(text_attribute("axis", "X"));
+    y_attributes.push(text_attribute("axis", "Y"));
+
+    let mut crs_attributes = vec![];
+    if geographic {
+        crs_attributes.push(text_attribute("grid_mapping_name", "latitude_longitude"));
+    }
+    let wkt = if is_specified(&r.configs.coordinate_ref_system_wkt) {
+        r.configs.coordinate_ref_system_wkt.clone()
+    } else if r.configs.epsg_code > 0 {
+        match esri_wkt_from_epsg(r.configs.epsg_code) {
+            s if s.starts_with("Unknown") => String::new(),
+            s => s,
+        }
+    } else {
+        String::new()
+    };
+    if !wkt.is_empty() {
+        crs_attributes.push(text_attribute("crs_wkt", &wkt));
+        crs_attributes.push(text_attribute("spatial_ref", &wkt));
+    }
+    if r.configs.epsg_code > 0 {
+        crs_attributes.push(text_attribute(
+            "epsg_code",
+            &format!("EPSG:{}", r.configs.epsg_code),
+        ));
+    }
+    crs_attributes.push(text_attribute(
+        "GeoTransform",
+        &format!(
+            "{} {} 0 {} 0 {}",
+            r.configs.west, r.configs.resolution_x, r.configs.north, -r.configs.resolution_y
+        ),
+    ));
+
+    let mut data_attributes = vec![];
+    if !r.configs.title.is_empty() {
+        data_attributes.push(text_attribute("long_name", &r.configs.title));
+    }
+    if is_specified(&r.configs.z_units) {
+        data_attributes.push(text_attribute("units", &r.configs.z_units));
+    }
+    data_attributes.push(number_attribute("_FillValue", data_type, r.configs.nodata));
+    data_attributes.push(text_attribute("grid_mapping", "crs"));
+
+    let data_size = rows * columns * type_size(data_type)?;
+    let vars = vec![
+        OutputVariable {
+            name: y_name.to_string(),
+            dims: vec![0],
+            attributes: y_attributes,
+            nc_type: NC_DOUBLE,
+            size: rows * 8,
+        },
+        OutputVariable {
+            name: x_name.to_string(),
+            dims: vec![1],
+            attributes: x_attributes,
+            nc_type: NC_DOUBLE,
+            size: columns * 8,
+        },
+        OutputVariable {
+            name: "crs".to_string(),
+            dims: vec![],
+            attributes: crs_attributes,
+            nc_type: NC_INT,
+            size: 4,
+        },
+        OutputVariable {
+            name: data_name,
+            dims: vec![0, 1],
+            attributes: data_attributes,
+            nc_type: data_type,
+            size: data_size,
+        },
+    ];
+    let mut global_attributes = vec![text_attribute("Conventions", "CF-1.8")];
+    if !r.configs.title.is_empty() {
+        global_attributes.push(text_attribute("title", &r.configs.title));
+    }
+    global_attributes.push(text_attribute(
+        "history",
+        &format!(
+            "{}: created by WhiteboxTools",
+            chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+        ),
+    ));
+    let dims = [(y_name, rows), (x_name, columns)];
+
+    // The header's length doesn't depend on the offsets of the data, and so it is encoded
+    // once to find where the data begin, and again with the offsets.
+    let total_size: usize = vars.iter().map(|v| v.size + padding(v.size)).sum();
+    let version = if total_size < i32::MAX as usize / 2 {
+        1
+    } else {
+        2
+    };
+    let zeros = vec![0u64; vars.len()];
+    let header_len = encode_header(version, &dims, &global_attributes, &vars, &zeros).len();
+    let mut begins = vec![];
+    let mut begin = header_len as u64;
+    for v in &vars {
+        begins.push(begin);
+        begin += (v.size + padding(v.size)) as u64;
+    }
+    let header = encode_header(version, &dims, &global_attributes, &vars, &begins);
+
+    let f = File::create(&r.file_name)?;
+    let mut writer = BufWriter::new(f);
+    writer.write_all(&header)?;
+
+    let mut bytes = Vec::with_capacity(rows.max(columns) * 8 + 4);
+    for row in 0..rows {
+        encode_value(&mut bytes, r.get_y_from_row(row as isize), NC_DOUBLE);
+    }
+    for column in 0..columns {
+        encode_value(&mut bytes, r.get_x_from_column(column as isize), NC_DOUBLE);
+    }
+    encode_value(&mut bytes, 0f64, NC_INT);
+    writer.write_all(&bytes)?;
+
+    for row in 0..rows {
+        bytes.clear();
+        for v in &r.data[row * columns..(row + 1) * columns] {
+            encode_value(&mut bytes, *v, data_type);
+        }
+        writer.write_all(&bytes)?;
+    }
+    writer.write_all(&vec![0u8; padding(data_size)])?;
+    writer.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+NOTES: A minimal PNG encoder, shared by the GeoPackage raster format, which stores 16-bit
+greyscale tiles, and the rendering tools, which write 8-bit RGBA images.
+*/
+
+use miniz_oxide::deflate::compress_to_vec_zlib;
+
+/// The eight bytes with which every PNG file begins.
+pub const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Encodes a PNG image from its scanlines, each of which is preceded by its filter type byte.
+/// The bit depth and colour type (e.g. 16 and 0 for greyscale, or 8 and 6 for RGBA) are those of
+/// the PNG image header; the image is written with default compression, filtering and no
+/// interlacing.
+pub fn encode_png(width: usize, height: usize, bit_depth: u8, colour_type: u8, scanlines: &[u8]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    header.extend_from_slice(&[bit_depth, colour_type, 0, 0, 0]);
+
+    let mut png = PNG_SIGNATURE.to_vec();
+    push_png_chunk(&mut png, b"IHDR", &header);
+    push_png_chunk(&mut png, b"IDAT", &compress_to_vec_zlib(scanlines, 6));
+    push_png_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn push_png_chunk(png: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    png.extend_from_slice(chunk_type);
+    png.extend_from_slice(data);
+    let crc = crc32(crc32(0xFFFF_FFFF, chunk_type), data) ^ 0xFFFF_FFFF;
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Updates a CRC-32 (as used by PNG, i.e. with the polynomial 0xEDB88320) with some bytes.
+pub fn crc32(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &b in bytes {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
@@ -0,0 +1,179 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+NOTES: The raster cache keeps recently read rasters in memory so that a process that runs
+tools repeatedly on the same inputs (e.g. a watershed delineation service built on the library,
+which may read the same DEM thousands of times) does not re-read and re-parse the files for
+every call. Entries are keyed by the canonical path of the file, along with the settings that
+select what is read from it (the band and time step of a netCDF file), and are only used while
+the file's modification time and size, and those of its data file for formats that split the
+header and data, are unchanged. Zarr stores and virtual mosaics are not cached, since their data
+are spread across chunk and tile files whose changes would go unnoticed. Rasters written by this process are also removed from the
+cache. The least recently used entries are evicted once the cached data exceed the
+raster_cache_mb setting; the cache is disabled when that setting is zero, as it is by default,
+because a process that runs a single tool gains nothing from it.
+*/
+
+use super::RasterConfigs;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// The modification times and sizes of the files of a raster.
+type Fingerprint = Vec<(SystemTime, u64)>;
+
+struct CacheEntry {
+    path: PathBuf,
+    options: String,
+    fingerprint: Fingerprint,
+    configs: RasterConfigs,
+    data: Vec<f64>,
+}
+
+impl CacheEntry {
+    fn size_in_bytes(&self) -> usize {
+        self.data.len() * 8
+    }
+}
+
+// Entries are ordered from the least to the most recently used.
+static RASTER_CACHE: Mutex<Vec<CacheEntry>> = Mutex::new(vec![]);
+
+/// Describes the settings that determine which part of a file is read, for the formats that
+/// hold more than one band.
+fn reader_options(ext: &str, netcdf_band: usize, netcdf_time: &str) -> String {
+    match ext {
+        "nc" => format!("band={};time={}", netcdf_band.max(1), netcdf_time.trim()),
+        _ => String::new(),
+    }
+}
+
+/// Returns the canonical path of a raster, the settings that it is read with, and the
+/// modification times and sizes of its files, or None if the raster can't be cached.
+fn identify(file_name: &str) -> Option<(PathBuf, String, Fingerprint)> {
+    let path = fs::canonicalize(file_name).ok()?;
+    let mut files = vec![path.clone()];
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let options = match ext.as_str() {
+        "zarr" | "wbvrt" => return None,
+        "nc" => {
+            let configs = whitebox_common::configs::get_configs().ok()?;
+            reader_options(&ext, configs.netcdf_band, &configs.netcdf_time)
+        }
+        _ => String::new(),
+    };
+    match ext.as_str() {
+        "dep" => files.push(path.with_extension("tas")),
+        "tas" => files.push(path.with_extension("dep")),
+        "bil" | "flt" => files.push(path.with_extension("hdr")),
+        "sdat" => files.push(path.with_extension("sgrd")),
+        "sgrd" => files.push(path.with_extension("sdat")),
+        "rdc" => files.push(path.with_extension("rst")),
+        "rst" => files.push(path.with_extension("rdc")),
+        _ => {}
+    }
+    let mut fingerprint = vec![];
+    for f in files {
+        let metadata = fs::metadata(&f).ok()?;
+        fingerprint.push((metadata.modified().ok()?, metadata.len()));
+    }
+    Some((path, options, fingerprint))
+}
+
+/// Returns a copy of a cached raster's configurations and data, if the file is cached and has
+/// not changed since it was read.
+pub(crate) fn get_cached_raster(file_name: &str) -> Option<(RasterConfigs, Vec<f64>)> {
+    let (path, options, fingerprint) = identify(file_name)?;
+    let mut cache = RASTER_CACHE.lock().ok()?;
+    let i = cache.iter().position(|e| e.path == path && e.options == options)?;
+    let entry = cache.remove(i);
+    if entry.fingerprint != fingerprint {
+        return None; // stale
+    }
+    let ret = (entry.configs.clone(), entry.data.clone());
+    cache.push(entry);
+    Some(ret)
+}
+
+/// Adds a raster that has just been read to the cache, evicting the least recently used
+/// entries as needed to keep the cache within `capacity` bytes.
+pub(crate) fn cache_raster(file_name: &str, configs: &RasterConfigs, data: &[f64], capacity: usize) {
+    let (path, options, fingerprint) = match identify(file_name) {
+        Some(v) => v,
+        None => return,
+    };
+    let entry = CacheEntry {
+        path,
+        options,
+        fingerprint,
+        configs: configs.clone(),
+        data: data.to_vec(),
+    };
+    if entry.size_in_bytes() > capacity {
+        return;
+    }
+    if let Ok(mut cache) = RASTER_CACHE.lock() {
+        cache.retain(|e| e.path != entry.path || e.options != entry.options);
+        let mut total: usize = cache.iter().map(|e| e.size_in_bytes()).sum();
+        while !cache.is_empty() && total + entry.size_in_bytes() > capacity {
+            total -= cache.remove(0).size_in_bytes();
+        }
+        cache.push(entry);
+    }
+}
+
+/// Removes a raster from the cache, e.g. when the file is overwritten.
+pub(crate) fn uncache_raster(file_name: &str) {
+    let path = match fs::canonicalize(file_name) {
+        Ok(p) => p,
+        Err(_) => Path::new(file_name).to_path_buf(),
+    };
+    if let Ok(mut cache) = RASTER_CACHE.lock() {
+        cache.retain(|e| e.path != path);
+    }
+}
+
+/// Empties the in-memory raster cache.
+pub fn clear_raster_cache() {
+    if let Ok(mut cache) = RASTER_CACHE.lock() {
+        cache.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{identify, reader_options};
+    use std::fs;
+
+    #[test]
+    fn test_reader_options() {
+        // the bands of a netCDF file are cached separately
+        assert_ne!(reader_options("nc", 1, ""), reader_options("nc", 2, ""));
+        assert_ne!(reader_options("nc", 1, ""), reader_options("nc", 1, "2020-07-01"));
+        assert_eq!(reader_options("nc", 0, ""), reader_options("nc", 1, ""));
+        assert_eq!(reader_options("tif", 2, "2020-07-01"), "");
+    }
+
+    #[test]
+    fn test_uncached_formats() {
+        let dir = std::env::temp_dir().join(format!("wbt_raster_cache_{}", std::process::id()));
+        let zarr = dir.join("dem.zarr");
+        fs::create_dir_all(&zarr).unwrap();
+        let tif = dir.join("dem.tif");
+        fs::write(&tif, [0u8; 8]).unwrap();
+        let vrt = dir.join("dem.wbvrt");
+        fs::write(&vrt, "{}").unwrap();
+        assert!(identify(&zarr.to_string_lossy()).is_none());
+        assert!(identify(&vrt.to_string_lossy()).is_none());
+        assert!(identify(&tif.to_string_lossy()).is_some());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,100 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Rasters are read from and written to files by default. A `RasterProvider` supplies rasters
+from somewhere else instead, e.g. the storage of an application that embeds the library and
+holds its rasters in memory or in a database, or of a web app using the wasm32 build, which has
+no file system. A provider is registered for a URL scheme (e.g. `idb`) and then
+handles every raster whose name begins with that scheme (e.g. `idb://dem.tif`). Because such names
+are URLs, they are left unchanged by the resolution of tool paths against the working directory
+and aren't checked for on disk, so tools read and write them like any other raster. In-memory
+rasters (`memory:`) are served by the built-in `MemoryRasterProvider`, which may also be registered
+for a scheme, e.g. to hold all of the rasters of a browser-based analysis in memory.
+
+Providers exchange the configurations and full-precision data of whole rasters; encoding them, or
+not, is up to the provider.
+*/
+
+use super::memory_rasters::{get_memory_raster, store_memory_raster};
+use super::{memory_raster_exists, RasterConfigs};
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+use std::sync::{Arc, Mutex};
+use whitebox_common::utils::{is_memory_dataset, is_url};
+
+/// A source and destination of rasters other than files.
+pub trait RasterProvider: Send + Sync {
+    /// Reads the raster named `file_name`, returning its configurations and data.
+    fn read(&self, file_name: &str) -> Result<(RasterConfigs, Vec<f64>), Error>;
+
+    /// Writes a raster, replacing any existing raster of the same name.
+    fn write(&self, file_name: &str, configs: &RasterConfigs, data: &[f64]) -> Result<(), Error>;
+
+    /// Returns true if a raster named `file_name` exists.
+    fn exists(&self, file_name: &str) -> bool;
+}
+
+/// Holds rasters in the memory of the process. It serves the `memory:` rasters and can be
+/// registered for a scheme with `register_raster_provider`.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct MemoryRasterProvider;
+
+impl RasterProvider for MemoryRasterProvider {
+    fn read(&self, file_name: &str) -> Result<(RasterConfigs, Vec<f64>), Error> {
+        get_memory_raster(file_name).ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("The in-memory raster {} does not exist.", file_name.trim()),
+            )
+        })
+    }
+
+    fn write(&self, file_name: &str, configs: &RasterConfigs, data: &[f64]) -> Result<(), Error> {
+        store_memory_raster(file_name, configs, data);
+        Ok(())
+    }
+
+    fn exists(&self, file_name: &str) -> bool {
+        memory_raster_exists(file_name)
+    }
+}
+
+static PROVIDERS: Mutex<Option<HashMap<String, Arc<dyn RasterProvider>>>> = Mutex::new(None);
+
+/// Registers `provider` for the rasters named with the URL scheme `scheme` (e.g. `idb` for
+/// `idb://dem.tif`), replacing any provider already registered for it. Schemes are not case
+/// sensitive, and a registered provider takes precedence over the built-in reading of URLs.
+pub fn register_raster_provider(scheme: &str, provider: Arc<dyn RasterProvider>) {
+    if let Ok(mut providers) = PROVIDERS.lock() {
+        providers
+            .get_or_insert_with(HashMap::new)
+            .insert(scheme.trim().to_lowercase(), provider);
+    }
+}
+
+/// Removes the provider registered for `scheme`, if any.
+pub fn unregister_raster_provider(scheme: &str) {
+    if let Ok(mut providers) = PROVIDERS.lock() {
+        if let Some(p) = providers.as_mut() {
+            p.remove(&scheme.trim().to_lowercase());
+        }
+    }
+}
+
+/// Returns the provider of the raster named `file_name`, or `None` if it is a file.
+pub(crate) fn raster_provider(file_name: &str) -> Option<Arc<dyn RasterProvider>> {
+    if is_memory_dataset(file_name) {
+        return Some(Arc::new(MemoryRasterProvider));
+    }
+    if !is_url(file_name) {
+        return None;
+    }
+    let file_name = file_name.trim();
+    let scheme = file_name[..file_name.find("://")?].to_lowercase();
+    let providers = PROVIDERS.lock().ok()?;
+    providers.as_ref()?.get(&scheme).cloned()
+}
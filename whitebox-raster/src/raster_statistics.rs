@@ -0,0 +1,290 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 17/10/2026
+License: MIT
+*/
+
+use super::is_nodata;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{Error, ErrorKind};
+
+/// The number of bins in the cached histogram.
+pub const HISTOGRAM_BINS: usize = 256;
+
+/// Summary statistics of a raster band. The statistics are cached in a PAM (`.aux.xml`)
+/// sidecar file alongside the raster when it is written, using the same keys as GDAL,
+/// so that tools and frontends (including QGIS) can reuse them without rescanning the data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RasterStatistics {
+    pub minimum: f64,
+    pub maximum: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    pub num_valid_cells: usize,
+    pub num_cells: usize,
+    /// Cell counts in `HISTOGRAM_BINS` equal-width bins spanning the minimum and maximum.
+    pub histogram: Vec<usize>,
+}
+
+impl RasterStatistics {
+    /// Calculates the statistics of a set of values, excluding nodata.
+    pub fn calculate(data: &[f64], nodata: f64) -> RasterStatistics {
+        let mut minimum = f64::INFINITY;
+        let mut maximum = f64::NEG_INFINITY;
+        let mut sum = 0f64;
+        let mut num_valid_cells = 0usize;
+        for &z in data {
+            if !is_nodata(z, nodata) {
+                if z < minimum {
+                    minimum = z;
+                }
+                if z > maximum {
+                    maximum = z;
+                }
+                sum += z;
+                num_valid_cells += 1;
+            }
+        }
+
+        let mut stats = RasterStatistics {
+            minimum,
+            maximum,
+            mean: f64::NAN,
+            std_dev: f64::NAN,
+            num_valid_cells,
+            num_cells: data.len(),
+            histogram: vec![0usize; HISTOGRAM_BINS],
+        };
+        if num_valid_cells == 0 {
+            return stats;
+        }
+
+        // a second pass avoids the loss of precision of the sum-of-squares formulation
+        let mean = sum / num_valid_cells as f64;
+        let mut sq_diff_sum = 0f64;
+        for &z in data {
+            if !is_nodata(z, nodata) {
+                sq_diff_sum += (z - mean) * (z - mean);
+                let bin = stats.bin(z);
+                stats.histogram[bin] += 1;
+            }
+        }
+        stats.mean = mean;
+        stats.std_dev = (sq_diff_sum / num_valid_cells as f64).sqrt();
+        stats
+    }
+
+    /// Returns the width of the histogram bins.
+    pub fn bin_width(&self) -> f64 {
+        (self.maximum - self.minimum) / HISTOGRAM_BINS as f64
+    }
+
+    fn bin(&self, z: f64) -> usize {
+        let width = self.bin_width();
+        if width <= 0f64 {
+            return 0;
+        }
+        (((z - self.minimum) / width).floor() as usize).min(HISTOGRAM_BINS - 1)
+    }
+
+    /// Estimates the value at a percentile (0-100) of the valid cells from the histogram,
+    /// e.g. for setting display ranges clipped to the tails of the distribution.
+    pub fn percentile(&self, percent: f64) -> f64 {
+        if self.num_valid_cells == 0 {
+            return f64::NAN;
+        }
+        let target = (percent / 100f64).clamp(0f64, 1f64) * self.num_valid_cells as f64;
+        let mut cumulative = 0f64;
+        for (bin, &count) in self.histogram.iter().enumerate() {
+            if count > 0 && cumulative + count as f64 >= target {
+                let fraction = (target - cumulative) / count as f64;
+                return self.minimum + (bin as f64 + fraction) * self.bin_width();
+            }
+            cumulative += count as f64;
+        }
+        self.maximum
+    }
+
+    /// Returns the statistics as a PAM dataset XML document.
+    pub fn to_aux_xml(&self) -> String {
+        let counts: Vec<String> = self.histogram.iter().map(|c| c.to_string()).collect();
+        let valid_percent = if self.num_cells > 0 {
+            100f64 * self.num_valid_cells as f64 / self.num_cells as f64
+        } else {
+            0f64
+        };
+        let mut s = String::from("<PAMDataset>\n  <PAMRasterBand band=\"1\">\n");
+        if self.num_valid_cells > 0 {
+            s.push_str("    <Histograms>\n      <HistItem>\n");
+            s.push_str(&format!("        <HistMin>{}</HistMin>\n", self.minimum));
+            s.push_str(&format!("        <HistMax>{}</HistMax>\n", self.maximum));
+            s.push_str(&format!("        <BucketCount>{}</BucketCount>\n", HISTOGRAM_BINS));
+            s.push_str("        <IncludeOutOfRange>0</IncludeOutOfRange>\n");
+            s.push_str("        <Approximate>0</Approximate>\n");
+            s.push_str(&format!("        <HistCounts>{}</HistCounts>\n", counts.join("|")));
+            s.push_str("      </HistItem>\n    </Histograms>\n");
+        }
+        s.push_str("    <Metadata>\n");
+        s.push_str(&format!("      <MDI key=\"STATISTICS_MAXIMUM\">{}</MDI>\n", self.maximum));
+        s.push_str(&format!("      <MDI key=\"STATISTICS_MEAN\">{}</MDI>\n", self.mean));
+        s.push_str(&format!("      <MDI key=\"STATISTICS_MINIMUM\">{}</MDI>\n", self.minimum));
+        s.push_str(&format!("      <MDI key=\"STATISTICS_STDDEV\">{}</MDI>\n", self.std_dev));
+        s.push_str(&format!("      <MDI key=\"STATISTICS_VALID_COUNT\">{}</MDI>\n", self.num_valid_cells));
+        s.push_str(&format!("      <MDI key=\"STATISTICS_VALID_PERCENT\">{}</MDI>\n", valid_percent));
+        s.push_str("    </Metadata>\n  </PAMRasterBand>\n</PAMDataset>\n");
+        s
+    }
+
+    /// Parses the statistics from a PAM dataset XML document. Documents written by other
+    /// software may lack the valid cell count, in which case it is derived from the valid
+    /// percentage and the number of cells in the raster. Returns None if the document does
+    /// not contain the minimum, maximum, mean, and standard deviation.
+    pub fn from_aux_xml(xml: &str, num_cells: usize) -> Option<RasterStatistics> {
+        let minimum = parse_mdi(xml, "STATISTICS_MINIMUM")?;
+        let maximum = parse_mdi(xml, "STATISTICS_MAXIMUM")?;
+        let mean = parse_mdi(xml, "STATISTICS_MEAN")?;
+        let std_dev = parse_mdi(xml, "STATISTICS_STDDEV")?;
+        let num_valid_cells = match parse_mdi(xml, "STATISTICS_VALID_COUNT") {
+            Some(n) => n as usize,
+            None => {
+                let percent = parse_mdi(xml, "STATISTICS_VALID_PERCENT").unwrap_or(100f64);
+                (percent / 100f64 * num_cells as f64).round() as usize
+            }
+        };
+        let mut histogram = vec![];
+        if let (Some(hist_min), Some(hist_max), Some(counts)) = (
+            parse_element(xml, "HistMin"),
+            parse_element(xml, "HistMax"),
+            parse_element(xml, "HistCounts"),
+        ) {
+            // only reuse a histogram with the same layout as that written by this library
+            if hist_min.parse::<f64>().ok() == Some(minimum)
+                && hist_max.parse::<f64>().ok() == Some(maximum)
+            {
+                histogram = counts
+                    .split('|')
+                    .filter_map(|c| c.trim().parse::<usize>().ok())
+                    .collect();
+            }
+        }
+        if histogram.len() != HISTOGRAM_BINS {
+            histogram = vec![0usize; HISTOGRAM_BINS];
+        }
+        Some(RasterStatistics {
+            minimum,
+            maximum,
+            mean,
+            std_dev,
+            num_valid_cells,
+            num_cells,
+            histogram,
+        })
+    }
+
+    /// Writes the statistics to the sidecar file of a raster.
+    pub fn write_sidecar(&self, raster_file: &str) -> Result<(), Error> {
+        let mut f = File::create(sidecar_file_name(raster_file))?;
+        f.write_all(self.to_aux_xml().as_bytes())?;
+        Ok(())
+    }
+
+    /// Reads the statistics from the sidecar file of a raster. An error is returned if the
+    /// sidecar does not exist, is older than the raster file, or cannot be parsed.
+    pub fn read_sidecar(raster_file: &str, num_cells: usize) -> Result<RasterStatistics, Error> {
+        let sidecar = sidecar_file_name(raster_file);
+        let raster_modified = fs::metadata(raster_file)?.modified()?;
+        let sidecar_modified = fs::metadata(&sidecar)?.modified()?;
+        if sidecar_modified < raster_modified {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("The statistics sidecar {} is out of date.", sidecar),
+            ));
+        }
+        let xml = fs::read_to_string(&sidecar)?;
+        match RasterStatistics::from_aux_xml(&xml, num_cells) {
+            Some(stats) => Ok(stats),
+            None => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("The statistics sidecar {} could not be parsed.", sidecar),
+            )),
+        }
+    }
+}
+
+/// Returns the name of the statistics sidecar file of a raster, i.e. the raster file
+/// name with an `.aux.xml` suffix.
+pub fn sidecar_file_name(raster_file: &str) -> String {
+    format!("{}.aux.xml", raster_file)
+}
+
+fn parse_mdi(xml: &str, key: &str) -> Option<f64> {
+    let tag = format!("<MDI key=\"{}\">", key);
+    let start = xml.find(&tag)? + tag.len();
+    let end = start + xml[start..].find("</MDI>")?;
+    xml[start..end].trim().parse::<f64>().ok()
+}
+
+fn parse_element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+    Some(&xml[start..end])
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RasterStatistics, HISTOGRAM_BINS};
+
+    #[test]
+    fn test_calculate() {
+        let nodata = -32768f64;
+        let data = [1f64, 2f64, nodata, 3f64, 4f64, nodata];
+        let stats = RasterStatistics::calculate(&data, nodata);
+        assert_eq!(stats.minimum, 1f64);
+        assert_eq!(stats.maximum, 4f64);
+        assert_eq!(stats.mean, 2.5f64);
+        assert!((stats.std_dev - 1.25f64.sqrt()).abs() < 1e-12);
+        assert_eq!(stats.num_valid_cells, 4);
+        assert_eq!(stats.num_cells, 6);
+        assert_eq!(stats.histogram.len(), HISTOGRAM_BINS);
+        assert_eq!(stats.histogram.iter().sum::<usize>(), 4);
+        assert_eq!(stats.histogram[0], 1);
+        assert_eq!(stats.histogram[HISTOGRAM_BINS - 1], 1);
+        assert_eq!(stats.percentile(0f64), 1f64);
+
+        // percentiles are estimated to within a bin of the histogram
+        let uniform: Vec<f64> = (0..=1000).map(|i| i as f64).collect();
+        let stats = RasterStatistics::calculate(&uniform, nodata);
+        assert!((stats.percentile(50f64) - 500f64).abs() <= stats.bin_width());
+        assert!((stats.percentile(95f64) - 950f64).abs() <= stats.bin_width());
+        assert_eq!(stats.percentile(150f64), stats.percentile(100f64));
+
+        let empty = RasterStatistics::calculate(&[nodata, nodata], nodata);
+        assert_eq!(empty.num_valid_cells, 0);
+        assert!(empty.mean.is_nan());
+        assert!(empty.percentile(50f64).is_nan());
+    }
+
+    #[test]
+    fn test_aux_xml() {
+        let data: Vec<f64> = (0..100).map(|i| (i as f64) / 4f64).collect();
+        let stats = RasterStatistics::calculate(&data, -32768f64);
+        let xml = stats.to_aux_xml();
+        assert_eq!(RasterStatistics::from_aux_xml(&xml, 100), Some(stats));
+
+        // statistics written by other software may lack the count and histogram
+        let xml = "<PAMDataset><PAMRasterBand band=\"1\"><Metadata>
+            <MDI key=\"STATISTICS_MAXIMUM\">10</MDI><MDI key=\"STATISTICS_MEAN\">5</MDI>
+            <MDI key=\"STATISTICS_MINIMUM\">0</MDI><MDI key=\"STATISTICS_STDDEV\">2</MDI>
+            <MDI key=\"STATISTICS_VALID_PERCENT\">50</MDI></Metadata></PAMRasterBand></PAMDataset>";
+        let stats = RasterStatistics::from_aux_xml(xml, 20).unwrap();
+        assert_eq!(stats.num_valid_cells, 10);
+        assert_eq!(stats.histogram, vec![0usize; HISTOGRAM_BINS]);
+        assert!(RasterStatistics::from_aux_xml("<PAMDataset/>", 20).is_none());
+    }
+}
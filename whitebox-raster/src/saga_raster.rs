@@ -6,6 +6,7 @@ use std::io::prelude::*;
 use std::io::{BufReader, BufWriter, Cursor, Error, ErrorKind, SeekFrom};
 use std::mem;
 use std::path::Path;
+use whitebox_common::errors::ErrorCause;
 
 pub fn read_saga(
     file_name: &String,
@@ -480,8 +481,7 @@ pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
             writer.write_all("DATAFORMAT\t= BYTE\n".as_bytes())?;
         }
         _ => {
-            return Err(Error::new(
-                ErrorKind::NotFound,
+            return Err(ErrorCause::UnsupportedFormat.error(
                 format!(
                     "Raster data type {:?} not supported in this format.",
                     r.configs.data_type
@@ -598,8 +598,7 @@ pub fn write_saga<'a>(r: &'a mut Raster) -> Result<(), Error> {
             }
         }
         _ => {
-            return Err(Error::new(
-                ErrorKind::NotFound,
+            return Err(ErrorCause::UnsupportedFormat.error(
                 "Raster data type is unsupported.",
             ));
         }
@@ -0,0 +1,635 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+NOTES: A TiledRaster provides access to a Whitebox raster without reading the whole grid into
+memory, which allows tools to process rasters that are larger than the available RAM (e.g. a
+100k x 100k LiDAR DEM). The grid is divided into square tiles, which are decoded on demand
+and held in a least-recently-used cache whose size is set by the tile_cache_mb setting. Tiles
+are read directly from the data file of the source raster, either from the index of a
+version 2 (tiled) file or by seeking within the rows of a version 1 (flat) file. Modified
+tiles that are evicted from the cache are spilled, uncompressed, to a scratch file next to the
+raster, which is removed when the TiledRaster is dropped. Output rasters are always written
+in the tiled version 2 format, using the compression named by the whitebox_raster_compression
+setting, or LZ4 when that setting is 'v1'.
+
+Random access (get_value, set_value) suits algorithms with local access patterns, such as
+flow routing, while read_rows and set_rows suit focal tools, which can process the raster in
+horizontal bands, each read as an ordinary in-memory Raster (see `is_out_of_core_input`).
+*/
+
+use super::*;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::SeekFrom;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The cache size used when the tile_cache_mb setting is zero.
+const DEFAULT_TILE_CACHE_MB: usize = 256;
+
+static SCRATCH_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+enum TileSource {
+    /// A new raster, whose tiles are initially filled with nodata.
+    None,
+    /// The data file of a version 1 Whitebox raster.
+    Flat(File),
+    /// The data file of a version 2 Whitebox raster.
+    Tiled(WhiteboxTileReader),
+}
+
+struct CachedTile {
+    values: Vec<f64>,
+    dirty: bool,
+    last_used: u64,
+}
+
+/// A raster whose data are held in a cache of tiles rather than in memory as a whole.
+///
+/// Example:
+///
+/// ```no_run
+/// # use whitebox_raster::TiledRaster;
+/// # fn main() -> std::io::Result<()> {
+/// # let (input_file, output_file) = ("dem.tif", "output.tif");
+/// let mut input = TiledRaster::open(input_file)?;
+/// let mut output = TiledRaster::initialize_using_config(output_file, &input.configs)?;
+/// for row in 0..input.configs.rows as isize {
+///     let values = input.get_row_data(row)?;
+///     // ...
+///     output.set_row_data(row, &values)?;
+/// }
+/// output.write()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TiledRaster {
+    pub file_name: String,
+    pub file_mode: String,
+    pub configs: RasterConfigs,
+    pub tile_size: usize,
+    tiles_across: usize,
+    source: TileSource,
+    // non-finite source values are replaced by the nodata value, as in Raster::new
+    replace_non_finite: bool,
+    scratch: Option<(String, File)>,
+    in_scratch: Vec<bool>,
+    cache: HashMap<usize, CachedTile>,
+    capacity: usize,
+    clock: u64,
+}
+
+impl TiledRaster {
+    /// Opens an existing Whitebox raster (.dep or .tas) for tiled access.
+    pub fn open(file_name: &str) -> Result<TiledRaster, Error> {
+        if !is_whitebox_file(file_name) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Out-of-core processing requires a Whitebox raster (.dep), but {} is not one.",
+                    file_name
+                ),
+            ));
+        }
+        let mut configs = RasterConfigs {
+            ..Default::default()
+        };
+        let format_version = read_whitebox_header(file_name, &mut configs)?;
+        let data_file = Path::new(file_name)
+            .with_extension("tas")
+            .into_os_string()
+            .into_string()
+            .unwrap();
+        let (source, tile_size) = if format_version >= 2 {
+            let reader = WhiteboxTileReader::open(&data_file)?;
+            if reader.rows != configs.rows || reader.columns != configs.columns {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "The dimensions of the Whitebox raster data file do not match its header.",
+                ));
+            }
+            configs.data_type = reader.data_type();
+            configs.endian = Endianness::LittleEndian;
+            let tile_size = reader.tile_size;
+            (TileSource::Tiled(reader), tile_size)
+        } else {
+            (TileSource::Flat(File::open(&data_file)?), V2_TILE_SIZE)
+        };
+        let replace_non_finite = configs.nodata.is_nan() || configs.nodata.is_infinite();
+        if replace_non_finite {
+            configs.nodata = -32768.0;
+        }
+        let mut r = TiledRaster::with_source(file_name, "r", configs, tile_size, source);
+        r.replace_non_finite = replace_non_finite;
+        Ok(r)
+    }
+
+    /// Creates a new tile-backed Whitebox raster with the grid extent and location specified
+    /// by `configs`. All cells are initially nodata.
+    pub fn initialize_using_config(
+        file_name: &str,
+        configs: &RasterConfigs,
+    ) -> Result<TiledRaster, Error> {
+        if !is_whitebox_file(file_name) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Out-of-core processing requires a Whitebox raster (.dep) output file, but {} is not one.",
+                    file_name
+                ),
+            ));
+        }
+        let mut configs = configs.clone();
+        configs.title = String::new();
+        configs.bands = 1;
+        configs.minimum = f64::INFINITY;
+        configs.maximum = f64::NEG_INFINITY;
+        configs.display_min = f64::INFINITY;
+        configs.display_max = f64::NEG_INFINITY;
+        configs.palette_nonlinearity = 1.0;
+        configs.reflect_at_edges = false;
+        configs.metadata = vec![];
+        Ok(TiledRaster::with_source(
+            file_name,
+            "w",
+            configs,
+            V2_TILE_SIZE,
+            TileSource::None,
+        ))
+    }
+
+    fn with_source(
+        file_name: &str,
+        file_mode: &str,
+        configs: RasterConfigs,
+        tile_size: usize,
+        source: TileSource,
+    ) -> TiledRaster {
+        let tiles_down = configs.rows.div_ceil(tile_size);
+        let tiles_across = configs.columns.div_ceil(tile_size);
+        let cache_mb = match whitebox_common::configs::get_configs() {
+            Ok(c) if c.tile_cache_mb > 0 => c.tile_cache_mb,
+            _ => DEFAULT_TILE_CACHE_MB,
+        };
+        TiledRaster {
+            file_name: file_name.to_string(),
+            file_mode: file_mode.to_string(),
+            configs,
+            tile_size,
+            tiles_across,
+            source,
+            replace_non_finite: false,
+            scratch: None,
+            in_scratch: vec![false; tiles_down * tiles_across],
+            cache: HashMap::new(),
+            capacity: (cache_mb * 1_048_576 / (tile_size * tile_size * 8)).max(1),
+            clock: 0,
+        }
+    }
+
+    /// Returns true if `file_name` should be processed out of core by tools that support it,
+    /// i.e. the tile_cache_mb setting is non-zero and the file is a Whitebox raster whose data
+    /// would not fit within the tile cache.
+    pub fn is_out_of_core_input(file_name: &str) -> bool {
+        let cache_mb = match whitebox_common::configs::get_configs() {
+            Ok(c) => c.tile_cache_mb,
+            Err(_) => 0,
+        };
+        if cache_mb == 0 || !is_whitebox_file(file_name) {
+            return false;
+        }
+        let mut configs = RasterConfigs {
+            ..Default::default()
+        };
+        match read_whitebox_header(file_name, &mut configs) {
+            Ok(_) => configs.rows * configs.columns * 8 > cache_mb * 1_048_576,
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the value of a cell, or nodata if the cell is outside of the raster.
+    pub fn get_value(&mut self, row: isize, column: isize) -> Result<f64, Error> {
+        if row < 0
+            || column < 0
+            || row as usize >= self.configs.rows
+            || column as usize >= self.configs.columns
+        {
+            return Ok(self.configs.nodata);
+        }
+        let (row, column) = (row as usize, column as usize);
+        let ts = self.tile_size;
+        let tile = (row / ts) * self.tiles_across + column / ts;
+        let nc = self.tile_extent(tile).3;
+        Ok(self.cached_tile(tile)?.values[(row % ts) * nc + column % ts])
+    }
+
+    /// Sets the value of a cell; cells outside of the raster are ignored.
+    pub fn set_value(&mut self, row: isize, column: isize, value: f64) -> Result<(), Error> {
+        if row < 0
+            || column < 0
+            || row as usize >= self.configs.rows
+            || column as usize >= self.configs.columns
+        {
+            return Ok(());
+        }
+        let (row, column) = (row as usize, column as usize);
+        let ts = self.tile_size;
+        let tile = (row / ts) * self.tiles_across + column / ts;
+        let nc = self.tile_extent(tile).3;
+        let cached = self.cached_tile(tile)?;
+        cached.values[(row % ts) * nc + column % ts] = value;
+        cached.dirty = true;
+        Ok(())
+    }
+
+    /// Returns the values of a row; rows outside of the raster are filled with nodata.
+    pub fn get_row_data(&mut self, row: isize) -> Result<Vec<f64>, Error> {
+        self.get_rows(row, 1)
+    }
+
+    /// Sets the values of a row.
+    pub fn set_row_data(&mut self, row: isize, values: &[f64]) -> Result<(), Error> {
+        self.set_rows(row, values)
+    }
+
+    /// Returns the values of `num_rows` consecutive rows, beginning with `start_row`, in
+    /// row-major order. Rows outside of the raster are filled with nodata. Each tile is only
+    /// visited once, such that the cache need not hold a full row of tiles.
+    pub fn get_rows(&mut self, start_row: isize, num_rows: usize) -> Result<Vec<f64>, Error> {
+        let columns = self.configs.columns;
+        let mut values = vec![self.configs.nodata; num_rows * columns];
+        let first = start_row.max(0) as usize;
+        let last = ((start_row + num_rows as isize).min(self.configs.rows as isize)).max(0) as usize;
+        if first >= last {
+            return Ok(values);
+        }
+        let ts = self.tile_size;
+        for tile_row in first / ts..=(last - 1) / ts {
+            for tile_col in 0..self.tiles_across {
+                let tile = tile_row * self.tiles_across + tile_col;
+                let (row_start, col_start, nr, nc) = self.tile_extent(tile);
+                let cached = self.cached_tile(tile)?;
+                for row in row_start.max(first)..(row_start + nr).min(last) {
+                    let i = (row as isize - start_row) as usize * columns + col_start;
+                    let j = (row - row_start) * nc;
+                    values[i..i + nc].copy_from_slice(&cached.values[j..j + nc]);
+                }
+            }
+        }
+        Ok(values)
+    }
+
+    /// Sets the values of consecutive rows, beginning with `start_row`, from a buffer holding
+    /// a whole number of rows in row-major order. Rows outside of the raster are ignored.
+    pub fn set_rows(&mut self, start_row: isize, values: &[f64]) -> Result<(), Error> {
+        let columns = self.configs.columns;
+        if !values.len().is_multiple_of(columns) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of values does not match a whole number of rows.",
+            ));
+        }
+        let num_rows = values.len() / columns;
+        let first = start_row.max(0) as usize;
+        let last = ((start_row + num_rows as isize).min(self.configs.rows as isize)).max(0) as usize;
+        if first >= last {
+            return Ok(());
+        }
+        let ts = self.tile_size;
+        for tile_row in first / ts..=(last - 1) / ts {
+            for tile_col in 0..self.tiles_across {
+                let tile = tile_row * self.tiles_across + tile_col;
+                let (row_start, col_start, nr, nc) = self.tile_extent(tile);
+                let cached = self.cached_tile(tile)?;
+                for row in row_start.max(first)..(row_start + nr).min(last) {
+                    let i = (row as isize - start_row) as usize * columns + col_start;
+                    let j = (row - row_start) * nc;
+                    cached.values[j..j + nc].copy_from_slice(&values[i..i + nc]);
+                }
+                cached.dirty = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads `num_rows` consecutive rows, beginning with `start_row`, into an in-memory
+    /// `Raster` covering that band of the grid. Rows outside of the raster are filled with
+    /// nodata, which allows a band to be padded by the radius of a focal operation, such that
+    /// band row `r` corresponds to row `start_row + r` of this raster.
+    pub fn read_rows(&mut self, start_row: isize, num_rows: usize) -> Result<Raster, Error> {
+        let data = self.get_rows(start_row, num_rows)?;
+        let mut configs = self.configs.clone();
+        configs.rows = num_rows;
+        configs.north = self.configs.north - start_row as f64 * self.configs.resolution_y;
+        configs.south = configs.north - num_rows as f64 * self.configs.resolution_y;
+        configs.reflect_at_edges = false;
+        Ok(Raster {
+            file_name: self.file_name.clone(),
+            file_mode: "r".to_string(),
+            raster_type: RasterType::Whitebox,
+            configs,
+            data,
+        })
+    }
+
+    pub fn is_nodata(&self, value: f64) -> bool {
+        is_nodata(value, self.configs.nodata)
+    }
+
+    pub fn add_metadata_entry(&mut self, value: String) {
+        self.configs.metadata.push(value);
+    }
+
+    pub fn num_cells(&self) -> usize {
+        self.configs.rows * self.configs.columns
+    }
+
+    /// Writes the raster as a version 2 Whitebox raster, one tile at a time.
+    pub fn write(&mut self) -> Result<(), Error> {
+        if !self.file_mode.contains("w") {
+            return Err(Error::other(
+                "Cannot write raster that is not created in write mmode ('w').",
+            ));
+        }
+        let compression = v2_compression(
+            &whitebox_common::configs::get_configs()?.whitebox_raster_compression,
+        )?
+        .unwrap_or(V2_LZ4);
+        let value_type = v2_value_type(&self.configs)?;
+        let rgb24 = self.configs.data_type == DataType::RGB24;
+        let file_name = self.file_name.clone();
+        let (tile_size, rows, columns) = (self.tile_size, self.configs.rows, self.configs.columns);
+        let nodata = self.configs.nodata;
+        let (mut minimum, mut maximum) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut n, mut sum, mut sq_sum) = (0usize, 0f64, 0f64);
+        let result = time_io(|| {
+            write_atomically(&file_name, |temp_file| {
+                let data_file = Path::new(temp_file)
+                    .with_extension("tas")
+                    .into_os_string()
+                    .into_string()
+                    .unwrap();
+                write_whitebox_v2_tiles(
+                    &data_file,
+                    tile_size,
+                    rows,
+                    columns,
+                    value_type,
+                    compression,
+                    rgb24,
+                    |tile| {
+                        let values = self.cached_tile(tile)?.values.clone();
+                        for &v in &values {
+                            if v != nodata {
+                                minimum = minimum.min(v);
+                                maximum = maximum.max(v);
+                                n += 1;
+                                sum += v;
+                                sq_sum += v * v;
+                            }
+                        }
+                        Ok(values)
+                    },
+                )?;
+                self.configs.minimum = minimum;
+                self.configs.maximum = maximum;
+                if self.configs.display_min == f64::INFINITY {
+                    self.configs.display_min = minimum;
+                }
+                if self.configs.display_max == f64::NEG_INFINITY {
+                    self.configs.display_max = maximum;
+                }
+                let mean = if n > 0 { sum / n as f64 } else { 0f64 };
+                let std_dev = if n > 0 {
+                    (sq_sum / n as f64 - mean * mean).max(0f64).sqrt()
+                } else {
+                    0f64
+                };
+                write_whitebox_header(
+                    temp_file,
+                    &mut self.configs,
+                    Some(compression),
+                    tile_size,
+                    (n, mean, std_dev),
+                )
+            })
+        });
+        uncache_raster(&self.file_name);
+        // any statistics sidecar left by an earlier version of the file is now stale
+        let _ = std::fs::remove_file(sidecar_file_name(&self.file_name));
+        result
+    }
+
+    /// Returns the extent of a tile as (starting row, starting column, rows, columns).
+    fn tile_extent(&self, tile: usize) -> (usize, usize, usize, usize) {
+        let row_start = (tile / self.tiles_across) * self.tile_size;
+        let col_start = (tile % self.tiles_across) * self.tile_size;
+        (
+            row_start,
+            col_start,
+            self.tile_size.min(self.configs.rows - row_start),
+            self.tile_size.min(self.configs.columns - col_start),
+        )
+    }
+
+    /// Returns a tile from the cache, loading it, and evicting the least recently used tile
+    /// if the cache is full, as needed.
+    fn cached_tile(&mut self, tile: usize) -> Result<&mut CachedTile, Error> {
+        self.clock += 1;
+        if !self.cache.contains_key(&tile) {
+            if self.cache.len() >= self.capacity {
+                self.evict()?;
+            }
+            let values = self.load_tile(tile)?;
+            self.cache.insert(
+                tile,
+                CachedTile {
+                    values,
+                    dirty: false,
+                    last_used: 0,
+                },
+            );
+        }
+        let cached = self.cache.get_mut(&tile).unwrap();
+        cached.last_used = self.clock;
+        Ok(cached)
+    }
+
+    fn evict(&mut self) -> Result<(), Error> {
+        let tile = match self.cache.iter().min_by_key(|(_, t)| t.last_used) {
+            Some((&tile, _)) => tile,
+            None => return Ok(()),
+        };
+        let cached = self.cache.remove(&tile).unwrap();
+        if cached.dirty {
+            let offset = self.scratch_offset(tile);
+            let file = self.scratch_file()?;
+            let mut bytes = Vec::with_capacity(cached.values.len() * 8);
+            for v in &cached.values {
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(&bytes)?;
+            self.in_scratch[tile] = true;
+        }
+        Ok(())
+    }
+
+    fn load_tile(&mut self, tile: usize) -> Result<Vec<f64>, Error> {
+        let extent = self.tile_extent(tile);
+        let (_, _, nr, nc) = extent;
+        if self.in_scratch[tile] {
+            let offset = self.scratch_offset(tile);
+            let file = self.scratch_file()?;
+            let mut bytes = vec![0u8; nr * nc * 8];
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut bytes)?;
+            return Ok(bytes
+                .chunks_exact(8)
+                .map(|b| f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+                .collect());
+        }
+        let nodata = self.configs.nodata;
+        let mut values = time_io(|| match &mut self.source {
+            TileSource::None => Ok(vec![nodata; nr * nc]),
+            TileSource::Flat(file) => read_whitebox_v1_block(file, &self.configs, extent),
+            TileSource::Tiled(reader) => {
+                reader.read_tile(tile / self.tiles_across, tile % self.tiles_across)
+            }
+        })?;
+        // snap values that differ from the nodata value only by rounding, as in Raster::new
+        for v in values.iter_mut() {
+            if (self.replace_non_finite && v.is_infinite()) || (*v != nodata && is_nodata(*v, nodata)) {
+                *v = nodata;
+            }
+        }
+        Ok(values)
+    }
+
+    fn scratch_offset(&self, tile: usize) -> u64 {
+        (tile * self.tile_size * self.tile_size * 8) as u64
+    }
+
+    /// Returns the scratch file that holds evicted modified tiles, creating it if necessary.
+    fn scratch_file(&mut self) -> Result<&mut File, Error> {
+        if self.scratch.is_none() {
+            let dir = match Path::new(&self.file_name).parent() {
+                Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+                _ => Path::new(".").to_path_buf(),
+            };
+            let scratch_name = dir
+                .join(format!(
+                    ".wbtiles-{}-{}",
+                    process::id(),
+                    SCRATCH_COUNTER.fetch_add(1, Ordering::SeqCst)
+                ))
+                .to_string_lossy()
+                .to_string();
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&scratch_name)?;
+            self.scratch = Some((scratch_name, file));
+        }
+        Ok(&mut self.scratch.as_mut().unwrap().1)
+    }
+}
+
+impl Drop for TiledRaster {
+    fn drop(&mut self) {
+        if let Some((scratch_name, _)) = self.scratch.take() {
+            let _ = std::fs::remove_file(scratch_name);
+        }
+    }
+}
+
+pub(crate) fn is_whitebox_file(file_name: &str) -> bool {
+    match Path::new(file_name).extension() {
+        Some(ext) => {
+            let ext = ext.to_string_lossy().to_lowercase();
+            ext == "dep" || ext == "tas"
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TiledRaster, V2_TILE_SIZE};
+    use crate::{DataType, RasterConfigs};
+    use std::fs;
+
+    #[test]
+    fn test_tiled_round_trip() {
+        let dir = std::env::temp_dir().join(format!("wbt_tiled_raster_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file_name = dir.join("tiled.dep").to_string_lossy().to_string();
+        // neither dimension is a whole number of tiles
+        let (rows, columns) = (V2_TILE_SIZE * 2 + 17, V2_TILE_SIZE + 41);
+        let mut configs = RasterConfigs {
+            ..Default::default()
+        };
+        configs.rows = rows;
+        configs.columns = columns;
+        configs.west = 0f64;
+        configs.north = rows as f64;
+        configs.east = columns as f64;
+        configs.south = 0f64;
+        configs.resolution_x = 1f64;
+        configs.resolution_y = 1f64;
+        configs.nodata = -32768f64;
+        configs.data_type = DataType::F64;
+        let expected = |row: usize, column: usize| (row * columns + column) as f64 * 0.25;
+
+        let mut output = TiledRaster::initialize_using_config(&file_name, &configs).unwrap();
+        // a cache of two tiles spills the others to the scratch file
+        output.capacity = 2;
+        let values: Vec<f64> = (0..rows * columns)
+            .map(|i| expected(i / columns, i % columns))
+            .collect();
+        output.set_rows(0, &values[..(rows - 1) * columns]).unwrap();
+        assert!(output.set_rows(0, &values[..columns + 1]).is_err());
+        output.set_value(5, V2_TILE_SIZE as isize + 3, configs.nodata).unwrap();
+        // cells outside of the raster are ignored
+        output.set_value(-1, 0, 1f64).unwrap();
+        output.set_value(0, columns as isize, 1f64).unwrap();
+        output.add_metadata_entry(String::from("Created by test_tiled_round_trip"));
+        output.write().unwrap();
+        drop(output);
+
+        let mut input = TiledRaster::open(&file_name).unwrap();
+        assert_eq!((input.configs.rows, input.configs.columns), (rows, columns));
+        input.capacity = 2;
+        for row in [0, 1, V2_TILE_SIZE - 1, V2_TILE_SIZE, rows - 2] {
+            let mut row_values = values[row * columns..(row + 1) * columns].to_vec();
+            if row == 5 {
+                row_values[V2_TILE_SIZE + 3] = configs.nodata;
+            }
+            assert_eq!(input.get_row_data(row as isize).unwrap(), row_values, "row {}", row);
+        }
+        // the last row was never set
+        assert_eq!(input.get_row_data(rows as isize - 1).unwrap(), vec![configs.nodata; columns]);
+        assert_eq!(input.get_value(5, V2_TILE_SIZE as isize + 3).unwrap(), configs.nodata);
+        assert_eq!(
+            input.get_value(rows as isize - 2, columns as isize - 1).unwrap(),
+            expected(rows - 2, columns - 1)
+        );
+        assert_eq!(input.get_value(-1, 0).unwrap(), configs.nodata);
+        assert_eq!(input.get_value(0, columns as isize).unwrap(), configs.nodata);
+        // a band of rows that runs past the edge of the raster is padded with nodata
+        let band = input.get_rows(rows as isize - 2, 3).unwrap();
+        assert_eq!(&band[..columns], &values[(rows - 2) * columns..(rows - 1) * columns]);
+        assert_eq!(&band[columns..], &vec![configs.nodata; 2 * columns][..]);
+        assert_eq!(input.configs.minimum, 0f64);
+        assert_eq!(input.configs.maximum, expected(rows - 2, columns - 1));
+        assert!(input.write().is_err());
+        drop(input);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,688 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+NOTES: A virtual mosaic (.wbvrt) is a JSON file listing member rasters, e.g. the tiles of a DEM,
+which is read as a single seamless raster, such that tiled data can be used by any tool without
+first being mosaicked into a new file. For example:
+
+    {
+      "type": "WhiteboxVirtualMosaic",
+      "members": [
+        { "file": "tiles/dem_001.tif", "west": 500000.0, "east": 501000.0,
+          "south": 4800000.0, "north": 4801000.0, "rows": 1000, "columns": 1000 },
+        { "file": "tiles/dem_002.tif", "west": 501000.0, "east": 502000.0,
+          "south": 4800000.0, "north": 4801000.0, "rows": 1000, "columns": 1000 }
+      ]
+    }
+
+Members may be in any raster format that can be read, and relative file names are relative to
+the directory of the mosaic (or, for a mosaic in an object store, to its prefix). The extent and
+size of each member are optional, but when any are missing the member is opened an extra time
+to find them; the BuildVirtualMosaic tool writes complete mosaics. The optional "nodata",
+"resolution_x" and "resolution_y" entries set the NoData value and cell size of the mosaic, which
+are otherwise those of the first member.
+
+The mosaic covers the union of the extents of its members. It is assembled in horizontal bands
+(see `VirtualMosaicReader`), opening in parallel only the members that overlap each band and
+closing them once they are passed, so a mosaic of many tiles never holds all of them in memory.
+Uncompressed GeoTIFF and Whitebox members are read by window, decoding only the rows that are
+needed. Members are sampled by nearest neighbour onto the grid of the mosaic, which is exact for
+members that share a grid (e.g. tiles), with later members in the list taking precedence over
+earlier ones where they overlap. NoData cells of a member never replace the values of other
+members. Members must share a coordinate reference system. Virtual mosaics are read-only.
+*/
+
+use super::*;
+use crate::tiled_raster::is_whitebox_file;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// A member raster of a virtual mosaic. The extent and size of a member are unknown (NaN and
+/// zero respectively) until they are found or listed.
+#[derive(Clone, Debug)]
+pub struct MosaicMember {
+    pub file_name: String,
+    pub west: f64,
+    pub east: f64,
+    pub south: f64,
+    pub north: f64,
+    pub rows: usize,
+    pub columns: usize,
+}
+
+impl MosaicMember {
+    pub fn new(file_name: &str) -> MosaicMember {
+        MosaicMember {
+            file_name: file_name.to_string(),
+            west: f64::NAN,
+            east: f64::NAN,
+            south: f64::NAN,
+            north: f64::NAN,
+            rows: 0,
+            columns: 0,
+        }
+    }
+
+    /// Creates a member, with the extent and size of a raster.
+    pub fn from_configs(file_name: &str, configs: &RasterConfigs) -> MosaicMember {
+        MosaicMember {
+            file_name: file_name.to_string(),
+            west: configs.west,
+            east: configs.east,
+            south: configs.south,
+            north: configs.north,
+            rows: configs.rows,
+            columns: configs.columns,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.west.is_finite()
+            && self.east.is_finite()
+            && self.south.is_finite()
+            && self.north.is_finite()
+            && self.east > self.west
+            && self.north > self.south
+            && self.rows > 0
+            && self.columns > 0
+    }
+}
+
+/// The contents of a virtual mosaic (.wbvrt) file.
+#[derive(Clone, Debug, Default)]
+pub struct VirtualMosaic {
+    pub members: Vec<MosaicMember>,
+    /// The NoData value of the mosaic, which is otherwise that of its first member.
+    pub nodata: Option<f64>,
+    /// The cell size (x and y) of the mosaic, which is otherwise that of its first member.
+    pub resolution: Option<(f64, f64)>,
+}
+
+impl VirtualMosaic {
+    /// Reads a virtual mosaic file, which may be in an object store.
+    pub fn read(file_name: &str) -> Result<VirtualMosaic, Error> {
+        let bytes = read_input(file_name)?;
+        let invalid = |message: String| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("The virtual mosaic {} {}", file_name, message),
+            )
+        };
+        let doc: Value = serde_json::from_slice(&bytes)
+            .map_err(|e| invalid(format!("could not be read: {}", e)))?;
+        let mut mosaic = VirtualMosaic::default();
+        for m in doc["members"].as_array().into_iter().flatten() {
+            let file = match m {
+                Value::String(s) => s.as_str(),
+                _ => m["file"].as_str().unwrap_or_default(),
+            };
+            if file.trim().is_empty() {
+                return Err(invalid("has a member with no file name.".to_string()));
+            }
+            let mut member = MosaicMember::new(file.trim());
+            member.west = m["west"].as_f64().unwrap_or(f64::NAN);
+            member.east = m["east"].as_f64().unwrap_or(f64::NAN);
+            member.south = m["south"].as_f64().unwrap_or(f64::NAN);
+            member.north = m["north"].as_f64().unwrap_or(f64::NAN);
+            member.rows = m["rows"].as_u64().unwrap_or(0) as usize;
+            member.columns = m["columns"].as_u64().unwrap_or(0) as usize;
+            mosaic.members.push(member);
+        }
+        if mosaic.members.is_empty() {
+            return Err(invalid("has no members.".to_string()));
+        }
+        mosaic.nodata = doc["nodata"].as_f64();
+        if let (Some(x), Some(y)) = (doc["resolution_x"].as_f64(), doc["resolution_y"].as_f64()) {
+            if x > 0f64 && y > 0f64 {
+                mosaic.resolution = Some((x, y));
+            }
+        }
+        Ok(mosaic)
+    }
+
+    /// Writes a virtual mosaic file, listing its members by the file names they were given.
+    pub fn write(&self, file_name: &str) -> Result<(), Error> {
+        let members: Vec<Value> = self
+            .members
+            .iter()
+            .map(|m| {
+                let mut member = Map::new();
+                member.insert("file".to_string(), json!(m.file_name));
+                if m.is_complete() {
+                    member.insert("west".to_string(), json!(m.west));
+                    member.insert("east".to_string(), json!(m.east));
+                    member.insert("south".to_string(), json!(m.south));
+                    member.insert("north".to_string(), json!(m.north));
+                    member.insert("rows".to_string(), json!(m.rows));
+                    member.insert("columns".to_string(), json!(m.columns));
+                }
+                Value::Object(member)
+            })
+            .collect();
+        let mut doc = Map::new();
+        doc.insert("type".to_string(), json!("WhiteboxVirtualMosaic"));
+        if let Some(nodata) = self.nodata {
+            doc.insert("nodata".to_string(), json!(nodata));
+        }
+        if let Some((x, y)) = self.resolution {
+            doc.insert("resolution_x".to_string(), json!(x));
+            doc.insert("resolution_y".to_string(), json!(y));
+        }
+        doc.insert("members".to_string(), Value::Array(members));
+        let contents = serde_json::to_string_pretty(&Value::Object(doc))?;
+        write_atomically(file_name, |temp_file| std::fs::write(temp_file, contents))
+    }
+
+    /// Returns the file name of a member, resolving relative file names against the location of
+    /// the mosaic.
+    pub fn member_path(mosaic_file: &str, member_file: &str) -> String {
+        if is_url(member_file) || Path::new(member_file).is_absolute() {
+            member_file.to_string()
+        } else if is_url(mosaic_file) {
+            let prefix = &mosaic_file[..mosaic_file.rfind('/').unwrap_or(0)];
+            format!("{}/{}", prefix, member_file.trim_start_matches("./"))
+        } else {
+            match Path::new(mosaic_file).parent() {
+                Some(dir) => dir.join(member_file).to_string_lossy().to_string(),
+                None => member_file.to_string(),
+            }
+        }
+    }
+}
+
+/// The number of mosaic rows assembled at a time when a virtual mosaic is read as a whole.
+const MOSAIC_BAND_ROWS: usize = 256;
+
+/// An open member of a virtual mosaic. Uncompressed GeoTIFFs are memory-mapped and Whitebox
+/// rasters are read by tile, such that only the rows that are sampled are decoded; members in
+/// other formats are read into memory as a whole.
+enum MemberSource {
+    Mapped(MappedRaster),
+    Tiled(TiledRaster),
+    Memory(Raster),
+}
+
+impl MemberSource {
+    fn open(file_name: &str) -> Result<MemberSource, Error> {
+        if MappedRaster::can_map(file_name) {
+            Ok(MemberSource::Mapped(MappedRaster::open(file_name)?))
+        } else if is_whitebox_file(file_name) && !is_url(file_name) && !is_memory_dataset(file_name) {
+            Ok(MemberSource::Tiled(TiledRaster::open(file_name)?))
+        } else {
+            Ok(MemberSource::Memory(Raster::new(file_name, "r")?))
+        }
+    }
+
+    fn configs(&self) -> &RasterConfigs {
+        match self {
+            MemberSource::Mapped(r) => &r.configs,
+            MemberSource::Tiled(r) => &r.configs,
+            MemberSource::Memory(r) => &r.configs,
+        }
+    }
+
+    fn get_row_data(&mut self, row: isize) -> Result<Vec<f64>, Error> {
+        match self {
+            MemberSource::Mapped(r) => Ok(r.get_row_data(row)),
+            MemberSource::Tiled(r) => r.get_row_data(row),
+            MemberSource::Memory(r) => Ok(r.get_row_data(row)),
+        }
+    }
+}
+
+/// Reads a virtual mosaic in horizontal bands, opening only the members that overlap each band.
+/// Members are closed once a band that they do not overlap is read, so reading a mosaic from
+/// top to bottom holds at most one band's worth of members open at a time.
+///
+/// Example:
+///
+/// ```no_run
+/// # use whitebox_raster::VirtualMosaicReader;
+/// # fn main() -> std::io::Result<()> {
+/// let mut input = VirtualMosaicReader::open("dem.wbvrt")?;
+/// let rows = input.configs.rows as isize;
+/// for row in (0..rows).step_by(100) {
+///     let values = input.get_rows(row, 100)?;
+///     // ...
+/// #   let _ = values;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct VirtualMosaicReader {
+    pub file_name: String,
+    /// The grid of the mosaic. Its data type is that of the members opened so far, which is
+    /// that of the whole mosaic once every member has been read.
+    pub configs: RasterConfigs,
+    members: Vec<MosaicMember>,
+    files: Vec<String>,
+    sources: Vec<Option<MemberSource>>,
+    data_types: Vec<Option<DataType>>,
+}
+
+impl VirtualMosaicReader {
+    /// Opens a virtual mosaic file, which may be in an object store. Only the first member, and
+    /// any members whose extents are not listed, are opened.
+    pub fn open(file_name: &str) -> Result<VirtualMosaicReader, Error> {
+        let mut mosaic = VirtualMosaic::read(file_name)?;
+        let files: Vec<String> = mosaic
+            .members
+            .iter()
+            .map(|m| VirtualMosaic::member_path(file_name, &m.file_name))
+            .collect();
+        for (i, file) in files.iter().enumerate() {
+            if get_raster_type_from_file(file.to_string(), "r".to_string()) == RasterType::VirtualMosaic
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The member {} of a virtual mosaic cannot itself be a virtual mosaic.",
+                        file
+                    ),
+                ));
+            }
+            // the extents of incompletely listed members are found by opening them
+            if !mosaic.members[i].is_complete() {
+                let source = MemberSource::open(file)?;
+                mosaic.members[i] =
+                    MosaicMember::from_configs(&mosaic.members[i].file_name, source.configs());
+            }
+        }
+
+        // The grid of the mosaic covers the extents of all of its members.
+        let first = &mosaic.members[0];
+        let (resolution_x, resolution_y) = mosaic.resolution.unwrap_or((
+            (first.east - first.west) / first.columns as f64,
+            (first.north - first.south) / first.rows as f64,
+        ));
+        let west = mosaic
+            .members
+            .iter()
+            .map(|m| m.west)
+            .fold(f64::INFINITY, f64::min);
+        let east = mosaic
+            .members
+            .iter()
+            .map(|m| m.east)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let south = mosaic
+            .members
+            .iter()
+            .map(|m| m.south)
+            .fold(f64::INFINITY, f64::min);
+        let north = mosaic
+            .members
+            .iter()
+            .map(|m| m.north)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let rows = ((north - south) / resolution_y).round().max(1f64) as usize;
+        let columns = ((east - west) / resolution_x).round().max(1f64) as usize;
+        let mut configs = RasterConfigs {
+            ..Default::default()
+        };
+        configs.rows = rows;
+        configs.columns = columns;
+        configs.west = west;
+        configs.north = north;
+        configs.resolution_x = resolution_x;
+        configs.resolution_y = resolution_y;
+        configs.east = west + columns as f64 * resolution_x;
+        configs.south = north - rows as f64 * resolution_y;
+
+        // The first member sets the NoData value and coordinate reference system of the mosaic.
+        let source = MemberSource::open(&files[0])?;
+        let m = source.configs();
+        configs.nodata = mosaic.nodata.unwrap_or(m.nodata);
+        configs.epsg_code = m.epsg_code;
+        configs.coordinate_ref_system_wkt = m.coordinate_ref_system_wkt.clone();
+        configs.projection = m.projection.clone();
+        configs.xy_units = m.xy_units.clone();
+        configs.z_units = m.z_units.clone();
+        configs.photometric_interp = m.photometric_interp;
+        configs.palette = m.palette.clone();
+        configs.data_type = m.data_type;
+        configs
+            .metadata
+            .push(format!("Virtual mosaic of {} rasters", files.len()));
+
+        let num_members = files.len();
+        let mut data_types = vec![None; num_members];
+        data_types[0] = Some(m.data_type);
+        let mut sources: Vec<Option<MemberSource>> = (0..num_members).map(|_| None).collect();
+        sources[0] = Some(source);
+        Ok(VirtualMosaicReader {
+            file_name: file_name.to_string(),
+            configs,
+            members: mosaic.members,
+            files,
+            sources,
+            data_types,
+        })
+    }
+
+    /// Returns the values of `num_rows` consecutive rows of the mosaic, beginning with
+    /// `start_row`, in row-major order. Cells that no member covers, and rows outside of the
+    /// mosaic, are NoData.
+    pub fn get_rows(&mut self, start_row: isize, num_rows: usize) -> Result<Vec<f64>, Error> {
+        let mut band = self.configs.clone();
+        band.rows = num_rows;
+        band.north = self.configs.north - start_row as f64 * self.configs.resolution_y;
+        band.south = band.north - num_rows as f64 * self.configs.resolution_y;
+        let overlaps: Vec<bool> = self
+            .members
+            .iter()
+            .map(|m| m.north > band.south && m.south < band.north)
+            .collect();
+
+        // members that the band no longer overlaps are closed, and those it newly overlaps opened
+        for (source, &overlap) in self.sources.iter_mut().zip(&overlaps) {
+            if !overlap {
+                *source = None;
+            }
+        }
+        let needed: Vec<usize> = (0..self.sources.len())
+            .filter(|&i| overlaps[i] && self.sources[i].is_none())
+            .collect();
+        self.open_members(&needed)?;
+
+        // later members take precedence over earlier ones
+        let mut values = vec![self.configs.nodata; num_rows * self.configs.columns];
+        for i in (0..self.sources.len()).filter(|&i| overlaps[i]) {
+            if let Some(source) = self.sources[i].as_mut() {
+                place_member(source, &band, &mut values)?;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Opens the listed members in parallel.
+    fn open_members(&mut self, members: &[usize]) -> Result<(), Error> {
+        if members.is_empty() {
+            return Ok(());
+        }
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().max_threads(members.len()).num_threads();
+        let files: Arc<Vec<(usize, String)>> =
+            Arc::new(members.iter().map(|&i| (i, self.files[i].clone())).collect());
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let files = files.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                for (i, file) in files.iter().skip(tid).step_by(num_procs) {
+                    let result = MemberSource::open(file);
+                    let failed = result.is_err();
+                    if tx.send((*i, result)).is_err() || failed {
+                        break;
+                    }
+                }
+            });
+        }
+        for _ in 0..members.len() {
+            let (i, result) = rx.recv().expect("Error receiving data from thread.");
+            let source = result?;
+            let epsg_code = source.configs().epsg_code;
+            if epsg_code != self.configs.epsg_code && epsg_code > 0 && self.configs.epsg_code > 0 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The members of the virtual mosaic {} must share a coordinate reference system, but {} (EPSG:{}) differs from the first member (EPSG:{}).",
+                        self.file_name, self.files[i], epsg_code, self.configs.epsg_code
+                    ),
+                ));
+            }
+            self.data_types[i] = Some(source.configs().data_type);
+            self.sources[i] = Some(source);
+        }
+        let data_types: Vec<DataType> = self.data_types.iter().flatten().copied().collect();
+        self.configs.data_type = if data_types.iter().all(|&t| t == data_types[0]) {
+            data_types[0]
+        } else if data_types.contains(&DataType::F64) {
+            DataType::F64
+        } else {
+            DataType::F32
+        };
+        Ok(())
+    }
+}
+
+pub fn read_virtual_mosaic(
+    file_name: &str,
+    configs: &mut RasterConfigs,
+    data: &mut Vec<f64>,
+) -> Result<(), Error> {
+    let mut reader = VirtualMosaicReader::open(file_name)?;
+    let rows = reader.configs.rows;
+    data.clear();
+    data.reserve(rows * reader.configs.columns);
+    for start_row in (0..rows).step_by(MOSAIC_BAND_ROWS) {
+        let num_rows = MOSAIC_BAND_ROWS.min(rows - start_row);
+        data.extend(reader.get_rows(start_row as isize, num_rows)?);
+    }
+    *configs = reader.configs.clone();
+    Ok(())
+}
+
+/// Samples a member raster, by nearest neighbour, onto the cells of a band of the mosaic that it
+/// covers, reading only the member rows that are needed. NoData cells of the member are skipped.
+fn place_member(
+    member: &mut MemberSource,
+    configs: &RasterConfigs,
+    data: &mut [f64],
+) -> Result<(), Error> {
+    let m = member.configs().clone();
+    let first_row = ((configs.north - m.north) / configs.resolution_y)
+        .floor()
+        .max(0f64) as usize;
+    let last_row =
+        (((configs.north - m.south) / configs.resolution_y).ceil().max(0f64) as usize).min(configs.rows);
+    let first_column = ((m.west - configs.west) / configs.resolution_x)
+        .floor()
+        .max(0f64) as usize;
+    let last_column =
+        (((m.east - configs.west) / configs.resolution_x).ceil() as usize).min(configs.columns);
+    let mut member_values: Option<(usize, Vec<f64>)> = None;
+    for row in first_row..last_row {
+        let y = configs.north - (row as f64 + 0.5) * configs.resolution_y;
+        let member_row = ((m.north - y) / m.resolution_y).floor();
+        if member_row < 0f64 || member_row >= m.rows as f64 {
+            continue;
+        }
+        let member_row = member_row as usize;
+        if member_values.as_ref().map(|(r, _)| *r) != Some(member_row) {
+            member_values = Some((member_row, member.get_row_data(member_row as isize)?));
+        }
+        let values = &member_values.as_ref().unwrap().1;
+        for column in first_column..last_column {
+            let x = configs.west + (column as f64 + 0.5) * configs.resolution_x;
+            let member_column = ((x - m.west) / m.resolution_x).floor();
+            if member_column < 0f64 || member_column >= m.columns as f64 {
+                continue;
+            }
+            let z = values[member_column as usize];
+            if !is_nodata(z, m.nodata) && !z.is_nan() {
+                data[row * configs.columns + column] = z;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn write_virtual_mosaic(r: &mut Raster) -> Result<(), Error> {
+    Err(Error::new(
+        ErrorKind::InvalidInput,
+        format!(
+            "Virtual mosaics are read-only, and so {} cannot be written; use another raster format, e.g. GeoTIFF, for outputs.",
+            r.get_short_filename()
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MemberSource, VirtualMosaic, VirtualMosaicReader};
+    use crate::{DataType, MosaicMember, Raster, RasterConfigs};
+    use std::fs;
+
+    fn write_member(file_name: &str, west: f64, north: f64, rows: usize, columns: usize, value: f64) {
+        let mut configs = RasterConfigs {
+            ..Default::default()
+        };
+        configs.rows = rows;
+        configs.columns = columns;
+        configs.west = west;
+        configs.north = north;
+        configs.east = west + columns as f64;
+        configs.south = north - rows as f64;
+        configs.resolution_x = 1f64;
+        configs.resolution_y = 1f64;
+        configs.nodata = -9999f64;
+        configs.data_type = DataType::F32;
+        let mut r = Raster::initialize_using_config(file_name, &configs);
+        for row in 0..rows as isize {
+            r.set_row_data(row, vec![value; columns]);
+        }
+        r.set_value(0, 0, configs.nodata);
+        r.write().unwrap();
+    }
+
+    #[test]
+    fn test_virtual_mosaic_bands() {
+        let dir = std::env::temp_dir().join(format!("wbt_mosaic_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = |f: &str| dir.join(f).to_string_lossy().to_string();
+        write_member(&path("a.dep"), 0f64, 4f64, 4, 4, 1f64);
+        fs::write(
+            path("b.asc"),
+            "ncols 4\nnrows 4\nxllcorner 4\nyllcorner 0\ncellsize 1\nNODATA_value -9999\n-9999 2 2 2\n2 2 2 2\n2 2 2 2\n2 2 2 2\n",
+        )
+        .unwrap();
+        write_member(&path("c.dep"), 2f64, 6f64, 4, 2, 3f64);
+        let mosaic_file = path("mosaic.wbvrt");
+        let mosaic = VirtualMosaic {
+            // the extent of c is found by opening it
+            members: vec![
+                MosaicMember::from_configs("a.dep", &Raster::new(&path("a.dep"), "r").unwrap().configs),
+                MosaicMember::from_configs("b.asc", &Raster::new(&path("b.asc"), "r").unwrap().configs),
+                MosaicMember::new("c.dep"),
+            ],
+            ..Default::default()
+        };
+        mosaic.write(&mosaic_file).unwrap();
+
+        let r = Raster::new(&mosaic_file, "r").unwrap();
+        assert_eq!((r.configs.rows, r.configs.columns), (6, 8));
+        let n = r.configs.nodata;
+        let expected = [
+            vec![n, n, n, 3.0, n, n, n, n],
+            vec![n, n, 3.0, 3.0, n, n, n, n],
+            vec![n, 1.0, 3.0, 3.0, n, 2.0, 2.0, 2.0],
+            vec![1.0, 1.0, 3.0, 3.0, 2.0, 2.0, 2.0, 2.0],
+            vec![1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 2.0],
+            vec![1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 2.0],
+        ];
+        for (row, expected_row) in expected.iter().enumerate() {
+            assert_eq!(&r.get_row_data(row as isize), expected_row, "row {}", row);
+        }
+
+        // a band opens only the members that it overlaps, and closes those that it no longer does
+        let mut reader = VirtualMosaicReader::open(&mosaic_file).unwrap();
+        assert!(matches!(reader.sources[0], Some(MemberSource::Tiled(_))));
+        let values = reader.get_rows(4, 3).unwrap();
+        assert_eq!(&values[..8], &expected[4][..]);
+        assert_eq!(&values[16..], &[n; 8][..]);
+        assert!(matches!(reader.sources[1], Some(MemberSource::Memory(_))));
+        assert!(reader.sources[2].is_none());
+        let values = reader.get_rows(0, 2).unwrap();
+        assert_eq!(values, [expected[0].clone(), expected[1].clone()].concat());
+        assert!(reader.sources[0].is_none() && reader.sources[1].is_none());
+        assert!(matches!(reader.sources[2], Some(MemberSource::Tiled(_))));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_virtual_mosaic_overlap_nodata() {
+        let dir = std::env::temp_dir().join(format!("wbt_mosaic_overlap_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = |f: &str| dir.join(f).to_string_lossy().to_string();
+        // two 3 x 3 members that overlap in the third column of the mosaic, each with its own
+        // NoData value and NoData cells
+        let write = |file_name: &str,
+                     west: f64,
+                     nodata: f64,
+                     value: f64,
+                     nodata_cells: &[(isize, isize)]| {
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = 3;
+            configs.columns = 3;
+            configs.west = west;
+            configs.east = west + 3f64;
+            configs.north = 3f64;
+            configs.south = 0f64;
+            configs.resolution_x = 1f64;
+            configs.resolution_y = 1f64;
+            configs.nodata = nodata;
+            configs.data_type = DataType::F32;
+            let mut r = Raster::initialize_using_config(&path(file_name), &configs);
+            for row in 0..3 {
+                r.set_row_data(row, vec![value; 3]);
+            }
+            for &(row, column) in nodata_cells {
+                r.set_value(row, column, nodata);
+            }
+            r.write().unwrap();
+            MosaicMember::from_configs(file_name, &configs)
+        };
+        let a = write("a.dep", 0f64, -9999f64, 1f64, &[(0, 0), (1, 2)]);
+        let b = write("b.dep", 2f64, -32768f64, 2f64, &[(0, 0), (1, 0)]);
+
+        // b takes precedence over a where they overlap, except where b is NoData, and the NoData
+        // cells of both members become the NoData value of the mosaic
+        let mosaic_file = path("ab.wbvrt");
+        let mosaic = VirtualMosaic {
+            members: vec![a.clone(), b.clone()],
+            nodata: Some(-1f64),
+            ..Default::default()
+        };
+        mosaic.write(&mosaic_file).unwrap();
+        let r = Raster::new(&mosaic_file, "r").unwrap();
+        assert_eq!((r.configs.rows, r.configs.columns), (3, 5));
+        assert_eq!(r.configs.nodata, -1f64);
+        let expected = [
+            [-1.0, 1.0, 1.0, 2.0, 2.0],
+            [1.0, 1.0, -1.0, 2.0, 2.0],
+            [1.0, 1.0, 2.0, 2.0, 2.0],
+        ];
+        for (row, expected_row) in expected.iter().enumerate() {
+            assert_eq!(r.get_row_data(row as isize), expected_row.to_vec(), "row {}", row);
+        }
+
+        // in the reverse order, a takes precedence, and the mosaic has the NoData value of b
+        let mosaic_file = path("ba.wbvrt");
+        let mosaic = VirtualMosaic {
+            members: vec![b, a],
+            ..Default::default()
+        };
+        mosaic.write(&mosaic_file).unwrap();
+        let r = Raster::new(&mosaic_file, "r").unwrap();
+        let n = -32768f64;
+        assert_eq!(r.configs.nodata, n);
+        let expected = [
+            [n, 1.0, 1.0, 2.0, 2.0],
+            [1.0, 1.0, n, 2.0, 2.0],
+            [1.0, 1.0, 1.0, 2.0, 2.0],
+        ];
+        for (row, expected_row) in expected.iter().enumerate() {
+            assert_eq!(r.get_row_data(row as isize), expected_row.to_vec(), "row {}", row);
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -6,15 +6,56 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::io::Error;
 use std::io::ErrorKind;
-use std::io::{BufReader, BufWriter, Cursor};
+use std::io::{BufReader, BufWriter, Cursor, SeekFrom};
 use std::mem;
 use std::path::Path;
+use std::sync::{mpsc, Arc};
+use whitebox_common::utils::spawn;
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use miniz_oxide::inflate::decompress_to_vec_zlib;
+use whitebox_common::errors::ErrorCause;
 
-pub fn read_whitebox(
-    file_name: &String,
-    configs: &mut RasterConfigs,
-    data: &mut Vec<f64>,
-) -> Result<(), Error> {
+/*
+Version 2 of the Whitebox raster format keeps the text .dep header of version 1, adding a
+'Format Version' entry along with the tile size, compression, statistics, and coordinate
+reference system of the raster, but stores the data (.tas) file as a series of independently
+compressed square tiles:
+
+    header:  b"WBT2", u32 tile size, u32 rows, u32 columns, u8 value type, u8 compression,
+             u16 reserved, u64 number of tiles
+    index:   (u64 offset, u64 length) of each tile
+    tiles:   the compressed tiles
+
+All numbers are little-endian. Tiles are stored in row-major order, as are the cells within a
+tile, and tiles along the bottom and right edges of the raster are not padded. The bytes of a
+tile's values are shuffled (all first bytes, then all second bytes, and so on) before it is
+compressed, which makes the compression of smoothly varying data far more effective. The
+index allows any tile to be read without reading the rest of the file (see
+`WhiteboxTileReader`). Version 2 files are only written when the whitebox_raster_compression
+setting is 'none', 'lz4', 'deflate', or 'zstd'; the default, 'v1', writes the original format.
+*/
+
+const V2_MAGIC: &[u8; 4] = b"WBT2";
+const V2_HEADER_SIZE: u64 = 28;
+pub(crate) const V2_TILE_SIZE: usize = 256;
+
+// tile value types
+const V2_U8: u8 = 1;
+const V2_I16: u8 = 2;
+const V2_F32: u8 = 3;
+const V2_F64: u8 = 4;
+const V2_U32: u8 = 5;
+
+// tile compression methods
+const V2_NONE: u8 = 0;
+pub(crate) const V2_LZ4: u8 = 1;
+const V2_DEFLATE: u8 = 2;
+const V2_ZSTD: u8 = 3;
+
+const V2_ZSTD_LEVEL: i32 = 3;
+
+/// Reads the .dep header of a Whitebox raster into `configs`, returning the format version.
+pub(crate) fn read_whitebox_header(file_name: &str, configs: &mut RasterConfigs) -> Result<u32, Error> {
     // read the header file
     // let header_file = file_name.replace(".tas", ".dep");
     let header_file = Path::new(&file_name)
@@ -25,12 +66,26 @@ pub fn read_whitebox(
     let f = File::open(header_file)?;
     let f = BufReader::new(f);
 
+    let mut format_version = 1;
     for line in f.lines() {
         let line_unwrapped = line.unwrap();
         // println!("{}", line_unwrapped);
-        let line_split = line_unwrapped.split(":");
+        let line_split = line_unwrapped.splitn(2, ":");
         let vec = line_split.collect::<Vec<&str>>();
-        if vec[0].to_lowercase().contains("rows") {
+        if vec[0].to_lowercase().contains("format version") {
+            format_version = vec[1].trim().parse::<u32>().unwrap_or(1);
+        } else if vec[0].to_lowercase().contains("epsg") {
+            configs.epsg_code = vec[1].trim().parse::<u16>().unwrap_or(0);
+        } else if vec[0].to_lowercase().contains("crs wkt") {
+            configs.coordinate_ref_system_wkt = vec[1].trim().to_string();
+        } else if vec[0].to_lowercase().contains("tile size")
+            || vec[0].to_lowercase().contains("compression")
+            || vec[0].to_lowercase().contains("mean")
+            || vec[0].to_lowercase().contains("std dev")
+            || vec[0].to_lowercase().contains("valid cells")
+        {
+            // informational entries of version 2 headers
+        } else if vec[0].to_lowercase().contains("rows") {
             configs.rows = vec[1].trim().parse::<f32>().unwrap() as usize;
         } else if vec[0].to_lowercase().contains("col") {
             configs.columns = vec[1].trim().parse::<f32>().unwrap() as usize;
@@ -117,6 +172,16 @@ pub fn read_whitebox(
     configs.resolution_x = (configs.east - configs.west) / configs.columns as f64;
     configs.resolution_y = (configs.north - configs.south) / configs.rows as f64;
 
+    Ok(format_version)
+}
+
+pub fn read_whitebox(
+    file_name: &String,
+    configs: &mut RasterConfigs,
+    data: &mut Vec<f64>,
+) -> Result<(), Error> {
+    let format_version = read_whitebox_header(file_name, configs)?;
+
     // read the data file
     // let data_file = file_name.replace(".dep", ".tas");
     let data_file = Path::new(&file_name)
@@ -124,25 +189,26 @@ pub fn read_whitebox(
         .into_os_string()
         .into_string()
         .unwrap();
+    if format_version >= 2 {
+        let mut reader = WhiteboxTileReader::open(&data_file)?;
+        if reader.rows != configs.rows || reader.columns != configs.columns {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The dimensions of the Whitebox raster data file do not match its header.",
+            ));
+        }
+        configs.data_type = reader.data_type();
+        configs.endian = Endianness::LittleEndian;
+        *data = reader.read_all()?;
+        return Ok(());
+    }
     let mut f = File::open(data_file.clone())?;
     //let br = BufReader::new(f);
     // let metadata = fs::metadata(data_file.clone())?;
     // let file_size: usize = metadata.len() as usize;
     // let mut buffer = vec![0; file_size];
 
-    let data_size = if configs.data_type == DataType::F64 {
-        8
-    } else if configs.data_type == DataType::F32
-        || configs.data_type == DataType::I32
-        || configs.data_type == DataType::RGBA32
-    {
-        4
-    } else if configs.data_type == DataType::I16 {
-        2
-    } else {
-        // DataType::Byte
-        1
-    };
+    let data_size = v1_value_size(configs.data_type);
 
     data.reserve(configs.rows * configs.columns);
 
@@ -238,45 +304,66 @@ pub fn read_whitebox(
     Ok(())
 }
 
-pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
-    // figure out the minimum and maximum values
-    for val in &r.data {
-        let v = *val;
-        if v != r.configs.nodata {
-            if v < r.configs.minimum {
-                r.configs.minimum = v;
-            }
-            if v > r.configs.maximum {
-                r.configs.maximum = v;
-            }
-        }
-    }
-
-    if r.configs.display_min == f64::INFINITY {
-        r.configs.display_min = r.configs.minimum;
-    }
-    if r.configs.display_max == f64::NEG_INFINITY {
-        r.configs.display_max = r.configs.maximum;
+/// Returns the number of bytes used to store each value in a version 1 data file.
+fn v1_value_size(data_type: DataType) -> usize {
+    match data_type {
+        DataType::F64 => 8,
+        DataType::F32 | DataType::I32 | DataType::RGBA32 => 4,
+        DataType::I16 => 2,
+        _ => 1, // DataType::Byte
     }
+}
 
-    // Delete the wstat file if it exists
-    // let wstat_string = r.file_name.replace(".tas", ".wstat").replace(".dep", ".wstat");
-    let wstat_string = Path::new(&r.file_name)
-        .with_extension("wstat")
-        .into_os_string()
-        .into_string()
-        .unwrap();
-    let wstat_path = Path::new(&wstat_string);
-    if wstat_path.exists() {
-        match std::fs::remove_file(&wstat_path) {
-            Ok(_) => {}  // do nothing
-            Err(_) => {} // do nothing
+/// Reads a rectangular block of cells (starting row, starting column, rows, columns) from the
+/// data file of a version 1 Whitebox raster, returning the values in row-major order.
+pub(crate) fn read_whitebox_v1_block(
+    file: &mut File,
+    configs: &RasterConfigs,
+    block: (usize, usize, usize, usize),
+) -> Result<Vec<f64>, Error> {
+    let (row_start, col_start, nr, nc) = block;
+    let size = v1_value_size(configs.data_type);
+    let mut values = Vec::with_capacity(nr * nc);
+    let mut buffer = vec![0u8; nc * size];
+    for row in row_start..row_start + nr {
+        file.seek(SeekFrom::Start(((row * configs.columns + col_start) * size) as u64))?;
+        file.read_exact(&mut buffer)?;
+        let mut bor = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+            Cursor::new(buffer.clone()),
+            configs.endian,
+        );
+        for _ in 0..nc {
+            values.push(match configs.data_type {
+                DataType::F64 => bor.read_f64()?,
+                DataType::F32 => bor.read_f32()? as f64,
+                DataType::I32 => bor.read_i32()? as f64,
+                DataType::I16 => bor.read_i16()? as f64,
+                DataType::U8 => bor.read_u8()? as f64,
+                DataType::RGBA32 => bor.read_f32()? as i32 as u32 as f64,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::NotFound,
+                        "Raster data type is unknown.",
+                    ));
+                }
+            });
         }
     }
+    Ok(values)
+}
 
+/// Writes the .dep header of a Whitebox raster. For version 2 files (`compression` is not
+/// None), `valid_stats` holds the number, mean, and standard deviation of the valid cells.
+pub(crate) fn write_whitebox_header(
+    file_name: &str,
+    configs: &mut RasterConfigs,
+    compression: Option<u8>,
+    tile_size: usize,
+    valid_stats: (usize, f64, f64),
+) -> Result<(), Error> {
     // Save the header file
     // let header_file = r.file_name.replace(".tas", ".dep");
-    let header_file = Path::new(&r.file_name)
+    let header_file = Path::new(file_name)
         .with_extension("dep")
         .into_os_string()
         .into_string()
@@ -284,40 +371,40 @@ pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
     let f = File::create(header_file)?;
     let mut writer = BufWriter::new(f);
 
-    let s = format!("Min:\t{}\n", r.configs.minimum);
+    let s = format!("Min:\t{}\n", configs.minimum);
     writer.write_all(s.as_bytes())?; //.expect("Unable to write data)
 
-    let s = format!("Max:\t{}\n", r.configs.maximum);
+    let s = format!("Max:\t{}\n", configs.maximum);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("North:\t{}\n", r.configs.north);
+    let s = format!("North:\t{}\n", configs.north);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("South:\t{}\n", r.configs.south);
+    let s = format!("South:\t{}\n", configs.south);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("East:\t{}\n", r.configs.east);
+    let s = format!("East:\t{}\n", configs.east);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("West:\t{}\n", r.configs.west);
+    let s = format!("West:\t{}\n", configs.west);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("Cols:\t{}\n", r.configs.columns);
+    let s = format!("Cols:\t{}\n", configs.columns);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("Rows:\t{}\n", r.configs.rows);
+    let s = format!("Rows:\t{}\n", configs.rows);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("Stacks:\t{}\n", r.configs.bands);
+    let s = format!("Stacks:\t{}\n", configs.bands);
     writer.write_all(s.as_bytes())?;
 
-    // if r.configs.photometric_interp == PhotometricInterpretation::RGB {
-    //     r.configs.data_type = DataType::I32;
+    // if configs.photometric_interp == PhotometricInterpretation::RGB {
+    //     configs.data_type = DataType::I32;
     // }
 
-    match r.configs.data_type {
+    match configs.data_type {
         DataType::F64 | DataType::U32 => {
-            if r.configs.photometric_interp != PhotometricInterpretation::RGB {
+            if configs.photometric_interp != PhotometricInterpretation::RGB {
                 // Java doesn't have an unsigned 32-bit integer, so Whitebox only has an I32.
                 writer.write_all("Data Type:\tDOUBLE\n".as_bytes())?;
             } else {
@@ -338,26 +425,25 @@ pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
             writer.write_all("Data Type:\tBYTE\n".as_bytes())?;
         }
         _ => {
-            return Err(Error::new(
-                ErrorKind::NotFound,
+            return Err(ErrorCause::UnsupportedFormat.error(
                 format!(
                     "Raster data type {:?} not supported in this format.",
-                    r.configs.data_type
+                    configs.data_type
                 ),
             ));
         }
     }
 
-    let s = format!("Z Units:\t{}\n", r.configs.z_units);
+    let s = format!("Z Units:\t{}\n", configs.z_units);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("XY Units:\t{}\n", r.configs.xy_units);
+    let s = format!("XY Units:\t{}\n", configs.xy_units);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("Projection:\t{}\n", r.configs.projection);
+    let s = format!("Projection:\t{}\n", configs.projection);
     writer.write_all(s.as_bytes())?;
 
-    match r.configs.photometric_interp {
+    match configs.photometric_interp {
         PhotometricInterpretation::Continuous => {
             writer.write_all("Data Scale:\tcontinuous\n".as_bytes())?;
         }
@@ -375,42 +461,138 @@ pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
         }
     }
 
-    let s = format!("Display Min:\t{}\n", r.configs.display_min);
+    let s = format!("Display Min:\t{}\n", configs.display_min);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("Display Max:\t{}\n", r.configs.display_max);
+    let s = format!("Display Max:\t{}\n", configs.display_max);
     writer.write_all(s.as_bytes())?;
 
-    if r.configs.palette == String::from("not specified") {
-        r.configs.palette = "grey.plt".to_string();
+    if configs.palette == String::from("not specified") {
+        configs.palette = "grey.plt".to_string();
     }
-    let s = format!("Preferred Palette:\t{}\n", r.configs.palette);
+    let s = format!("Preferred Palette:\t{}\n", configs.palette);
     writer.write_all(s.as_bytes())?;
 
-    let s = format!("NoData:\t{}\n", r.configs.nodata);
+    let s = format!("NoData:\t{}\n", configs.nodata);
     writer.write_all(s.as_bytes())?;
 
-    if r.configs.endian == Endianness::LittleEndian {
+    if configs.endian == Endianness::LittleEndian {
         writer.write_all("Byte Order:\tLITTLE_ENDIAN\n".as_bytes())?;
     } else {
         writer.write_all("Byte Order:\tBIG_ENDIAN\n".as_bytes())?;
     }
 
-    if r.configs.palette_nonlinearity < 0.0 {
-        r.configs.palette_nonlinearity = 1.0;
+    if configs.palette_nonlinearity < 0.0 {
+        configs.palette_nonlinearity = 1.0;
     }
     let s = format!(
         "Palette Nonlinearity:\t{}\n",
-        r.configs.palette_nonlinearity
+        configs.palette_nonlinearity
     );
     writer.write_all(s.as_bytes())?;
 
-    for md in &r.configs.metadata {
+    for md in &configs.metadata {
         let s = format!("Metadata Entry:\t{}\n", md.replace(":", ";"));
         writer.write_all(s.as_bytes())?;
     }
 
+    if let Some(compression) = compression {
+        writer.write_all("Format Version:\t2\n".as_bytes())?;
+
+        let s = format!("Tile Size:\t{}\n", tile_size);
+        writer.write_all(s.as_bytes())?;
+
+        let s = match compression {
+            V2_LZ4 => "Compression:\tLZ4\n",
+            V2_DEFLATE => "Compression:\tDEFLATE\n",
+            V2_ZSTD => "Compression:\tZSTD\n",
+            _ => "Compression:\tNONE\n",
+        };
+        writer.write_all(s.as_bytes())?;
+
+        let (n, mean, std_dev) = valid_stats;
+        let s = format!("Mean:\t{}\n", mean);
+        writer.write_all(s.as_bytes())?;
+
+        let s = format!("Std Dev:\t{}\n", std_dev);
+        writer.write_all(s.as_bytes())?;
+
+        let s = format!("Valid Cells:\t{}\n", n);
+        writer.write_all(s.as_bytes())?;
+
+        let s = format!("EPSG:\t{}\n", configs.epsg_code);
+        writer.write_all(s.as_bytes())?;
+
+        let s = format!(
+            "CRS WKT:\t{}\n",
+            configs.coordinate_ref_system_wkt.replace("\n", " ").replace("\r", " ")
+        );
+        writer.write_all(s.as_bytes())?;
+    }
+
     let _ = writer.flush();
+    Ok(())
+}
+
+pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
+    let compression = v2_compression(
+        &whitebox_common::configs::get_configs()?.whitebox_raster_compression,
+    )?;
+
+    // figure out the minimum and maximum values
+    for val in &r.data {
+        let v = *val;
+        if v != r.configs.nodata {
+            if v < r.configs.minimum {
+                r.configs.minimum = v;
+            }
+            if v > r.configs.maximum {
+                r.configs.maximum = v;
+            }
+        }
+    }
+
+    if r.configs.display_min == f64::INFINITY {
+        r.configs.display_min = r.configs.minimum;
+    }
+    if r.configs.display_max == f64::NEG_INFINITY {
+        r.configs.display_max = r.configs.maximum;
+    }
+
+    // Delete the wstat file if it exists
+    // let wstat_string = r.file_name.replace(".tas", ".wstat").replace(".dep", ".wstat");
+    let wstat_string = Path::new(&r.file_name)
+        .with_extension("wstat")
+        .into_os_string()
+        .into_string()
+        .unwrap();
+    let wstat_path = Path::new(&wstat_string);
+    if wstat_path.exists() {
+        match std::fs::remove_file(&wstat_path) {
+            Ok(_) => {}  // do nothing
+            Err(_) => {} // do nothing
+        }
+    }
+
+    let mut valid_stats = (0usize, 0f64, 0f64);
+    if compression.is_some() {
+        let (mut n, mut sum, mut sq_sum) = (0usize, 0f64, 0f64);
+        for &v in &r.data {
+            if v != r.configs.nodata {
+                n += 1;
+                sum += v;
+                sq_sum += v * v;
+            }
+        }
+        let mean = if n > 0 { sum / n as f64 } else { 0f64 };
+        let std_dev = if n > 0 {
+            (sq_sum / n as f64 - mean * mean).max(0f64).sqrt()
+        } else {
+            0f64
+        };
+        valid_stats = (n, mean, std_dev);
+    }
+    write_whitebox_header(&r.file_name, &mut r.configs, compression, V2_TILE_SIZE, valid_stats)?;
 
     // write the data file
     // let data_file = r.file_name.replace(".dep", ".tas");
@@ -419,6 +601,9 @@ pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
         .into_os_string()
         .into_string()
         .unwrap();
+    if let Some(compression) = compression {
+        return write_whitebox_v2_data(r, &data_file, compression);
+    }
     let f = File::create(&data_file)?;
     let mut writer = BufWriter::new(f);
 
@@ -492,3 +677,513 @@ pub fn write_whitebox<'a>(r: &'a mut Raster) -> Result<(), Error> {
 
     Ok(())
 }
+
+/// Returns the v2 tile compression named by the whitebox_raster_compression setting, or None
+/// if the original (v1) format should be written.
+pub(crate) fn v2_compression(setting: &str) -> Result<Option<u8>, Error> {
+    match setting.trim().to_lowercase().as_str() {
+        "" | "v1" => Ok(None),
+        "none" => Ok(Some(V2_NONE)),
+        "lz4" => Ok(Some(V2_LZ4)),
+        "deflate" => Ok(Some(V2_DEFLATE)),
+        "zstd" => Ok(Some(V2_ZSTD)),
+        s => Err(ErrorCause::UnsupportedFormat.error(
+            format!(
+                "Unsupported Whitebox raster compression '{}'; use 'v1', 'none', 'lz4', 'deflate', or 'zstd'.",
+                s
+            ),
+        )),
+    }
+}
+
+fn v2_value_size(value_type: u8) -> usize {
+    match value_type {
+        V2_U8 => 1,
+        V2_I16 => 2,
+        V2_F32 | V2_U32 => 4,
+        _ => 8,
+    }
+}
+
+/// Returns the extent (starting row, starting column, rows, columns) of a tile.
+fn v2_tile_extent(
+    tile_size: usize,
+    rows: usize,
+    columns: usize,
+    tile_row: usize,
+    tile_col: usize,
+) -> (usize, usize, usize, usize) {
+    let row_start = tile_row * tile_size;
+    let col_start = tile_col * tile_size;
+    (
+        row_start,
+        col_start,
+        tile_size.min(rows - row_start),
+        tile_size.min(columns - col_start),
+    )
+}
+
+/// Returns the tile value type used to store a raster with the given configurations.
+pub(crate) fn v2_value_type(configs: &RasterConfigs) -> Result<u8, Error> {
+    match configs.data_type {
+        DataType::F64 | DataType::U32 => {
+            if configs.photometric_interp != PhotometricInterpretation::RGB {
+                Ok(V2_F64)
+            } else {
+                Ok(V2_U32)
+            }
+        }
+        DataType::F32 | DataType::U16 | DataType::I32 => Ok(V2_F32),
+        DataType::RGBA32 | DataType::RGB24 => Ok(V2_U32),
+        DataType::I16 => Ok(V2_I16),
+        DataType::U8 | DataType::I8 => Ok(V2_U8),
+        _ => Err(Error::new(
+            ErrorKind::NotFound,
+            "Raster data type is unknown.",
+        )),
+    }
+}
+
+/// Encodes and compresses the values of one tile, in row-major order.
+pub(crate) fn encode_v2_tile(
+    values: &[f64],
+    value_type: u8,
+    compression: u8,
+    rgb24: bool,
+) -> Result<Vec<u8>, Error> {
+    let size = v2_value_size(value_type);
+    let mut bytes = Vec::with_capacity(values.len() * size);
+    for &v in values {
+        match value_type {
+            V2_U8 => bytes.push(v as u8),
+            V2_I16 => bytes.extend_from_slice(&(v as i16).to_le_bytes()),
+            V2_F32 => bytes.extend_from_slice(&(v as f32).to_le_bytes()),
+            V2_U32 => {
+                let val = if rgb24 { (255 << 24) as u32 | v as u32 } else { v as u32 };
+                bytes.extend_from_slice(&val.to_le_bytes())
+            }
+            _ => bytes.extend_from_slice(&v.to_le_bytes()),
+        }
+    }
+    let bytes = shuffle_bytes(&bytes, size);
+    match compression {
+        V2_LZ4 => Ok(lz4_flex::block::compress(&bytes)),
+        V2_DEFLATE => Ok(compress_to_vec_zlib(&bytes, 6)),
+        V2_ZSTD => zstd_codec::compress(&bytes, V2_ZSTD_LEVEL),
+        _ => Ok(bytes),
+    }
+}
+
+fn write_whitebox_v2_data(r: &Raster, data_file: &str, compression: u8) -> Result<(), Error> {
+    let value_type = v2_value_type(&r.configs)?;
+    let rgb24 = r.configs.data_type == DataType::RGB24;
+    let rows = r.configs.rows;
+    let columns = r.configs.columns;
+    let tiles_down = (rows + V2_TILE_SIZE - 1) / V2_TILE_SIZE;
+    let tiles_across = (columns + V2_TILE_SIZE - 1) / V2_TILE_SIZE;
+    let num_tiles = tiles_down * tiles_across;
+
+    // compress the tiles in parallel
+    let num_procs = whitebox_common::utils::ThreadPool::from_settings().max_threads(num_tiles).num_threads();
+    let values = Arc::new(r.data.clone());
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let values = values.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            for tile in (0..num_tiles).filter(|t| t % num_procs == tid) {
+                let (row_start, col_start, nr, nc) = v2_tile_extent(
+                    V2_TILE_SIZE,
+                    rows,
+                    columns,
+                    tile / tiles_across,
+                    tile % tiles_across,
+                );
+                let mut tile_values = Vec::with_capacity(nr * nc);
+                for row in row_start..row_start + nr {
+                    tile_values.extend_from_slice(
+                        &values[row * columns + col_start..row * columns + col_start + nc],
+                    );
+                }
+                let blob = encode_v2_tile(&tile_values, value_type, compression, rgb24);
+                tx.send((tile, blob)).unwrap();
+            }
+        });
+    }
+    let mut tiles: Vec<Vec<u8>> = vec![vec![]; num_tiles];
+    for _ in 0..num_tiles {
+        let (tile, blob) = rx.recv().expect("Error receiving data from thread.");
+        tiles[tile] = blob?;
+    }
+
+    let f = File::create(data_file)?;
+    let mut writer = BufWriter::new(f);
+    write_v2_data_header(&mut writer, V2_TILE_SIZE, rows, columns, value_type, compression)?;
+    let mut offset = V2_HEADER_SIZE + 16 * num_tiles as u64;
+    for blob in &tiles {
+        writer.write_u64::<LittleEndian>(offset)?;
+        writer.write_u64::<LittleEndian>(blob.len() as u64)?;
+        offset += blob.len() as u64;
+    }
+    for blob in &tiles {
+        writer.write_all(blob)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_v2_data_header<W: Write>(
+    writer: &mut W,
+    tile_size: usize,
+    rows: usize,
+    columns: usize,
+    value_type: u8,
+    compression: u8,
+) -> Result<(), Error> {
+    let num_tiles = ((rows + tile_size - 1) / tile_size) * ((columns + tile_size - 1) / tile_size);
+    writer.write_all(V2_MAGIC)?;
+    writer.write_u32::<LittleEndian>(tile_size as u32)?;
+    writer.write_u32::<LittleEndian>(rows as u32)?;
+    writer.write_u32::<LittleEndian>(columns as u32)?;
+    writer.write_u8(value_type)?;
+    writer.write_u8(compression)?;
+    writer.write_u16::<LittleEndian>(0u16)?;
+    writer.write_u64::<LittleEndian>(num_tiles as u64)?;
+    Ok(())
+}
+
+/// Writes a v2 data file one tile at a time, such that the raster never needs to be held in
+/// memory as a whole. `get_tile` returns the values of a tile, given its number.
+pub(crate) fn write_whitebox_v2_tiles<F>(
+    data_file: &str,
+    tile_size: usize,
+    rows: usize,
+    columns: usize,
+    value_type: u8,
+    compression: u8,
+    rgb24: bool,
+    mut get_tile: F,
+) -> Result<(), Error>
+where
+    F: FnMut(usize) -> Result<Vec<f64>, Error>,
+{
+    let num_tiles = ((rows + tile_size - 1) / tile_size) * ((columns + tile_size - 1) / tile_size);
+    let f = File::create(data_file)?;
+    let mut writer = BufWriter::new(f);
+    write_v2_data_header(&mut writer, tile_size, rows, columns, value_type, compression)?;
+    // the index is written once the sizes of the compressed tiles are known
+    writer.write_all(&vec![0u8; 16 * num_tiles])?;
+    let mut index = Vec::with_capacity(num_tiles);
+    let mut offset = V2_HEADER_SIZE + 16 * num_tiles as u64;
+    for tile in 0..num_tiles {
+        let blob = encode_v2_tile(&get_tile(tile)?, value_type, compression, rgb24)?;
+        writer.write_all(&blob)?;
+        index.push((offset, blob.len() as u64));
+        offset += blob.len() as u64;
+    }
+    writer.seek(SeekFrom::Start(V2_HEADER_SIZE))?;
+    for (offset, len) in index {
+        writer.write_u64::<LittleEndian>(offset)?;
+        writer.write_u64::<LittleEndian>(len)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Groups the bytes of a buffer of values by their significance, i.e. the first byte of every
+/// value, followed by the second byte of every value, and so on.
+fn shuffle_bytes(bytes: &[u8], size: usize) -> Vec<u8> {
+    if size == 1 {
+        return bytes.to_vec();
+    }
+    let n = bytes.len() / size;
+    let mut out = vec![0u8; bytes.len()];
+    for i in 0..n {
+        for b in 0..size {
+            out[b * n + i] = bytes[i * size + b];
+        }
+    }
+    out
+}
+
+fn unshuffle_bytes(bytes: &[u8], size: usize) -> Vec<u8> {
+    if size == 1 {
+        return bytes.to_vec();
+    }
+    let n = bytes.len() / size;
+    let mut out = vec![0u8; bytes.len()];
+    for i in 0..n {
+        for b in 0..size {
+            out[i * size + b] = bytes[b * n + i];
+        }
+    }
+    out
+}
+
+/// Decompresses and decodes one tile of a v2 Whitebox raster data file.
+fn decode_v2_tile(
+    blob: &[u8],
+    compression: u8,
+    value_type: u8,
+    num_values: usize,
+) -> Result<Vec<f64>, Error> {
+    let size = v2_value_size(value_type);
+    let bytes = match compression {
+        V2_LZ4 => lz4_flex::block::decompress(blob, num_values * size).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "Error encountered while decoding an LZ4 compressed Whitebox raster tile.",
+            )
+        })?,
+        V2_DEFLATE => decompress_to_vec_zlib(blob).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "Error encountered while decoding a DEFLATE compressed Whitebox raster tile.",
+            )
+        })?,
+        V2_ZSTD => zstd_codec::decompress(blob, num_values * size).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                "Error encountered while decoding a ZSTD compressed Whitebox raster tile.",
+            )
+        })?,
+        _ => blob.to_vec(),
+    };
+    if bytes.len() != num_values * size {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "A Whitebox raster tile has an unexpected size.",
+        ));
+    }
+    let bytes = unshuffle_bytes(&bytes, size);
+    Ok(bytes
+        .chunks_exact(size)
+        .map(|b| match value_type {
+            V2_U8 => b[0] as f64,
+            V2_I16 => i16::from_le_bytes([b[0], b[1]]) as f64,
+            V2_F32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            V2_U32 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            _ => f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]),
+        })
+        .collect())
+}
+
+/// Provides random access to the tiles of a version 2 Whitebox raster, without reading the
+/// whole data file. Tiles are numbered from the top-left corner of the raster, and the cells
+/// of a tile are returned in row-major order.
+pub struct WhiteboxTileReader {
+    file: File,
+    pub tile_size: usize,
+    pub rows: usize,
+    pub columns: usize,
+    value_type: u8,
+    compression: u8,
+    index: Vec<(u64, u64)>,
+    // the most recently read tile, which speeds up get_value
+    cached_tile: Option<(usize, Vec<f64>)>,
+}
+
+impl WhiteboxTileReader {
+    /// Opens a v2 Whitebox raster; `file_name` may name either its .dep or .tas file.
+    pub fn open(file_name: &str) -> Result<WhiteboxTileReader, Error> {
+        let data_file = Path::new(file_name)
+            .with_extension("tas")
+            .into_os_string()
+            .into_string()
+            .unwrap();
+        let mut file = File::open(&data_file)?;
+        let mut header = [0u8; V2_HEADER_SIZE as usize];
+        if file.read_exact(&mut header).is_err() || &header[0..4] != V2_MAGIC {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("{} is not a version 2 Whitebox raster data file.", data_file),
+            ));
+        }
+        let mut bor = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+            Cursor::new(header.to_vec()),
+            Endianness::LittleEndian,
+        );
+        bor.seek(4);
+        let tile_size = bor.read_u32()? as usize;
+        let rows = bor.read_u32()? as usize;
+        let columns = bor.read_u32()? as usize;
+        let value_type = bor.read_u8()?;
+        let compression = bor.read_u8()?;
+        bor.read_u16()?;
+        let num_tiles = bor.read_u64()? as usize;
+        if tile_size == 0
+            || num_tiles != ((rows + tile_size - 1) / tile_size) * ((columns + tile_size - 1) / tile_size)
+            || value_type < V2_U8
+            || value_type > V2_U32
+            || compression > V2_ZSTD
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("The header of the Whitebox raster data file {} is invalid.", data_file),
+            ));
+        }
+        let mut buffer = vec![0u8; 16 * num_tiles];
+        file.read_exact(&mut buffer)?;
+        let mut bor = ByteOrderReader::<Cursor<Vec<u8>>>::new(
+            Cursor::new(buffer),
+            Endianness::LittleEndian,
+        );
+        let mut index = Vec::with_capacity(num_tiles);
+        for _ in 0..num_tiles {
+            index.push((bor.read_u64()?, bor.read_u64()?));
+        }
+        Ok(WhiteboxTileReader {
+            file,
+            tile_size,
+            rows,
+            columns,
+            value_type,
+            compression,
+            index,
+            cached_tile: None,
+        })
+    }
+
+    /// Returns the data type in which the raster's values are stored.
+    pub fn data_type(&self) -> DataType {
+        match self.value_type {
+            V2_U8 => DataType::U8,
+            V2_I16 => DataType::I16,
+            V2_F32 => DataType::F32,
+            V2_U32 => DataType::RGBA32,
+            _ => DataType::F64,
+        }
+    }
+
+    pub fn tiles_down(&self) -> usize {
+        (self.rows + self.tile_size - 1) / self.tile_size
+    }
+
+    pub fn tiles_across(&self) -> usize {
+        (self.columns + self.tile_size - 1) / self.tile_size
+    }
+
+    /// Returns the extent of a tile as (starting row, starting column, rows, columns).
+    pub fn tile_extent(&self, tile_row: usize, tile_col: usize) -> (usize, usize, usize, usize) {
+        v2_tile_extent(self.tile_size, self.rows, self.columns, tile_row, tile_col)
+    }
+
+    /// Reads and decodes a single tile.
+    pub fn read_tile(&mut self, tile_row: usize, tile_col: usize) -> Result<Vec<f64>, Error> {
+        if tile_row >= self.tiles_down() || tile_col >= self.tiles_across() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Tile ({}, {}) is outside of the raster.", tile_row, tile_col),
+            ));
+        }
+        let blob = self.read_blob(tile_row * self.tiles_across() + tile_col)?;
+        let (_, _, nr, nc) = self.tile_extent(tile_row, tile_col);
+        decode_v2_tile(&blob, self.compression, self.value_type, nr * nc)
+    }
+
+    /// Returns the value of a single cell, reading the tile that contains it if necessary.
+    pub fn get_value(&mut self, row: usize, column: usize) -> Result<f64, Error> {
+        if row >= self.rows || column >= self.columns {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Cell ({}, {}) is outside of the raster.", row, column),
+            ));
+        }
+        let (tile_row, tile_col) = (row / self.tile_size, column / self.tile_size);
+        let tile = tile_row * self.tiles_across() + tile_col;
+        if self.cached_tile.as_ref().map_or(true, |(t, _)| *t != tile) {
+            let values = self.read_tile(tile_row, tile_col)?;
+            self.cached_tile = Some((tile, values));
+        }
+        let (row_start, col_start, _, nc) = self.tile_extent(tile_row, tile_col);
+        let values = &self.cached_tile.as_ref().unwrap().1;
+        Ok(values[(row - row_start) * nc + column - col_start])
+    }
+
+    /// Reads the whole raster in row-major order, decompressing the tiles in parallel.
+    pub fn read_all(&mut self) -> Result<Vec<f64>, Error> {
+        let mut blobs = Vec::with_capacity(self.index.len());
+        for tile in 0..self.index.len() {
+            blobs.push(self.read_blob(tile)?);
+        }
+        let blobs = Arc::new(blobs);
+        let num_tiles = blobs.len();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().max_threads(num_tiles).num_threads();
+        let (tile_size, rows, columns) = (self.tile_size, self.rows, self.columns);
+        let (value_type, compression) = (self.value_type, self.compression);
+        let tiles_across = self.tiles_across();
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let blobs = blobs.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                for tile in (0..num_tiles).filter(|t| t % num_procs == tid) {
+                    let (_, _, nr, nc) = v2_tile_extent(
+                        tile_size,
+                        rows,
+                        columns,
+                        tile / tiles_across,
+                        tile % tiles_across,
+                    );
+                    let values = decode_v2_tile(&blobs[tile], compression, value_type, nr * nc);
+                    tx.send((tile, values)).unwrap();
+                }
+            });
+        }
+        let mut data = vec![0f64; rows * columns];
+        for _ in 0..num_tiles {
+            let (tile, values) = rx.recv().expect("Error receiving data from thread.");
+            let values = values?;
+            let (row_start, col_start, nr, nc) =
+                self.tile_extent(tile / tiles_across, tile % tiles_across);
+            for r in 0..nr {
+                let start = (row_start + r) * columns + col_start;
+                data[start..start + nc].copy_from_slice(&values[r * nc..(r + 1) * nc]);
+            }
+        }
+        Ok(data)
+    }
+
+    fn read_blob(&mut self, tile: usize) -> Result<Vec<u8>, Error> {
+        let (offset, len) = self.index[tile];
+        let mut blob = vec![0u8; len as usize];
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.file.read_exact(&mut blob)?;
+        Ok(blob)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        decode_v2_tile, encode_v2_tile, v2_compression, V2_DEFLATE, V2_F32, V2_F64, V2_I16,
+        V2_LZ4, V2_NONE, V2_U8, V2_ZSTD,
+    };
+
+    #[test]
+    fn test_v2_tile_codecs() {
+        let values: Vec<f64> = (0..1000).map(|i| ((i % 37) as f64 - 5.0) * 0.5).collect();
+        for compression in [V2_NONE, V2_LZ4, V2_DEFLATE, V2_ZSTD] {
+            for value_type in [V2_F64, V2_F32, V2_I16, V2_U8] {
+                let expected: Vec<f64> = match value_type {
+                    V2_I16 => values.iter().map(|&v| v as i16 as f64).collect(),
+                    V2_U8 => values.iter().map(|&v| v as u8 as f64).collect(),
+                    _ => values.clone(),
+                };
+                let blob = encode_v2_tile(&values, value_type, compression, false).unwrap();
+                let decoded = decode_v2_tile(&blob, compression, value_type, values.len()).unwrap();
+                assert_eq!(decoded, expected, "compression {}, value type {}", compression, value_type);
+                // a tile that decodes to the wrong number of values is an error
+                assert!(decode_v2_tile(&blob, compression, value_type, values.len() + 1).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_v2_compression() {
+        assert_eq!(v2_compression("v1").unwrap(), None);
+        assert_eq!(v2_compression("ZSTD").unwrap(), Some(V2_ZSTD));
+        assert_eq!(v2_compression("lz4").unwrap(), Some(V2_LZ4));
+        assert!(v2_compression("brotli").is_err());
+    }
+}
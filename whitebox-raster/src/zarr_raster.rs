@@ -0,0 +1,1754 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Reading and writing rasters stored as Zarr (.zarr) stores, in version 2 or 3 of the Zarr
+format, which is the chunked array format of Pangeo-style (xarray and dask) big-data stacks. A
+store is a directory of metadata and chunk files, or the same keys under a prefix in an object
+store (e.g. s3://bucket/dem.zarr), in which case the chunks are fetched with concurrent requests
+and only the chunks of the selected band are transferred.
+
+Reading uses the array at the root of the store or, for a group, the first array with two or more
+dimensions that isn't a coordinate array, as listed by the group's consolidated metadata or, for
+a local store, by its subdirectories. Groups in object stores must have consolidated metadata,
+since their keys can't otherwise be listed. As with NetCDF files, the last two dimensions are the
+y and x dimensions and the --band flag selects among the bands of any leading dimensions. Chunks
+may be uncompressed or compressed with zstd, zlib, gzip, lz4, or Blosc (with its lz4, zlib, or
+zstd codecs and byte shuffling), and version 3 arrays may be sharded. Missing chunks hold the
+fill value. Following the conventions of xarray and rioxarray, the grid is georeferenced from the
+coordinate arrays of the x and y dimensions (named by the _ARRAY_DIMENSIONS attribute, or by
+dimension_names) or, failing that, from the GeoTransform attribute of the grid mapping array, and
+CF packing (scale_factor and add_offset) is undone.
+
+Writing creates a group holding a two-dimensional array named after the file, in chunks of
+512 x 512 cells compressed with zstd, with x/y (or lon/lat) coordinate arrays, a 'spatial_ref'
+grid mapping array that holds the WKT and GeoTransform of the raster, and consolidated metadata,
+such that the store can be opened directly with xarray.open_zarr. The format version of outputs is
+set by the zarr_version setting (2 by default). Chunks containing only nodata cells are not
+written. Chunks are read and decoded, and encoded and written, in parallel.
+*/
+
+use super::*;
+use miniz_oxide::inflate::{decompress_to_vec, decompress_to_vec_zlib};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+use whitebox_common::utils::{is_url, read_input};
+
+/// The number of rows and columns of the chunks of outputs.
+const CHUNK_SIZE: usize = 512;
+const ZSTD_LEVEL: i32 = 3;
+/// The number of chunks that are requested at once from an object store.
+const REMOTE_REQUESTS: usize = 16;
+
+const BLOSC_HEADER_LEN: usize = 16;
+const BLOSC_DOSHUFFLE: u8 = 0x1;
+const BLOSC_MEMCPYED: u8 = 0x2;
+const BLOSC_DOBITSHUFFLE: u8 = 0x4;
+const BLOSC_NOSPLIT: u8 = 0x10;
+const BLOSC_MAX_SPLITS: usize = 16;
+const BLOSC_MIN_BUFFERSIZE: usize = 128;
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+/// Returns a JSON number, or one of the strings used for non-finite values, as a number.
+fn json_number(v: &Value) -> Option<f64> {
+    match v {
+        Value::Number(n) => n.as_f64(),
+        Value::Bool(b) => Some(if *b { 1f64 } else { 0f64 }),
+        Value::String(s) => match s.as_str() {
+            "NaN" => Some(f64::NAN),
+            "Infinity" => Some(f64::INFINITY),
+            "-Infinity" => Some(f64::NEG_INFINITY),
+            _ => s.trim().parse::<f64>().ok(),
+        },
+        Value::Array(a) if a.len() == 1 => json_number(&a[0]),
+        _ => None,
+    }
+}
+
+/// A JSON fill value, which is a number or, for non-finite values, a string.
+fn fill_json(value: f64, dtype: ZarrType) -> Value {
+    if value.is_nan() {
+        json!("NaN")
+    } else if value.is_infinite() {
+        json!(if value > 0f64 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        })
+    } else if dtype.is_integer() {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ZarrType {
+    Bool,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+}
+
+impl ZarrType {
+    /// Parses a version 2 data type, e.g. '<f4', returning the type and whether it is big-endian.
+    fn from_v2(dtype: &str) -> Option<(ZarrType, bool)> {
+        if dtype.len() != 3 {
+            return None;
+        }
+        let t = match &dtype[1..] {
+            "b1" => ZarrType::Bool,
+            "i1" => ZarrType::I8,
+            "i2" => ZarrType::I16,
+            "i4" => ZarrType::I32,
+            "i8" => ZarrType::I64,
+            "u1" => ZarrType::U8,
+            "u2" => ZarrType::U16,
+            "u4" => ZarrType::U32,
+            "u8" => ZarrType::U64,
+            "f4" => ZarrType::F32,
+            "f8" => ZarrType::F64,
+            _ => return None,
+        };
+        Some((t, dtype.starts_with('>')))
+    }
+
+    fn from_v3(name: &str) -> Option<ZarrType> {
+        Some(match name {
+            "bool" => ZarrType::Bool,
+            "int8" => ZarrType::I8,
+            "int16" => ZarrType::I16,
+            "int32" => ZarrType::I32,
+            "int64" => ZarrType::I64,
+            "uint8" => ZarrType::U8,
+            "uint16" => ZarrType::U16,
+            "uint32" => ZarrType::U32,
+            "uint64" => ZarrType::U64,
+            "float32" => ZarrType::F32,
+            "float64" => ZarrType::F64,
+            _ => return None,
+        })
+    }
+
+    fn v2_name(self) -> &'static str {
+        match self {
+            ZarrType::Bool => "|b1",
+            ZarrType::I8 => "|i1",
+            ZarrType::I16 => "<i2",
+            ZarrType::I32 => "<i4",
+            ZarrType::I64 => "<i8",
+            ZarrType::U8 => "|u1",
+            ZarrType::U16 => "<u2",
+            ZarrType::U32 => "<u4",
+            ZarrType::U64 => "<u8",
+            ZarrType::F32 => "<f4",
+            ZarrType::F64 => "<f8",
+        }
+    }
+
+    fn v3_name(self) -> &'static str {
+        match self {
+            ZarrType::Bool => "bool",
+            ZarrType::I8 => "int8",
+            ZarrType::I16 => "int16",
+            ZarrType::I32 => "int32",
+            ZarrType::I64 => "int64",
+            ZarrType::U8 => "uint8",
+            ZarrType::U16 => "uint16",
+            ZarrType::U32 => "uint32",
+            ZarrType::U64 => "uint64",
+            ZarrType::F32 => "float32",
+            ZarrType::F64 => "float64",
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            ZarrType::Bool | ZarrType::I8 | ZarrType::U8 => 1,
+            ZarrType::I16 | ZarrType::U16 => 2,
+            ZarrType::I32 | ZarrType::U32 | ZarrType::F32 => 4,
+            ZarrType::I64 | ZarrType::U64 | ZarrType::F64 => 8,
+        }
+    }
+
+    fn is_integer(self) -> bool {
+        self != ZarrType::F32 && self != ZarrType::F64
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            ZarrType::Bool | ZarrType::U8 => DataType::U8,
+            ZarrType::I8 => DataType::I8,
+            ZarrType::I16 => DataType::I16,
+            ZarrType::I32 => DataType::I32,
+            ZarrType::I64 => DataType::I64,
+            ZarrType::U16 => DataType::U16,
+            ZarrType::U32 => DataType::U32,
+            ZarrType::U64 => DataType::U64,
+            ZarrType::F32 => DataType::F32,
+            ZarrType::F64 => DataType::F64,
+        }
+    }
+
+    /// Returns the type in which a raster is written, i.e. that of its data type, unless its
+    /// nodata value can't be represented by an integer type, in which case a float is used.
+    fn for_raster(configs: &RasterConfigs) -> ZarrType {
+        let t = match configs.data_type {
+            DataType::F64 => return ZarrType::F64,
+            DataType::I64 => ZarrType::I64,
+            DataType::I32 => ZarrType::I32,
+            DataType::I16 => ZarrType::I16,
+            DataType::I8 => ZarrType::I8,
+            DataType::U64 => ZarrType::U64,
+            DataType::U32 => ZarrType::U32,
+            DataType::U16 => ZarrType::U16,
+            DataType::U8 => ZarrType::U8,
+            _ => return ZarrType::F32,
+        };
+        let (min_value, max_value) = configs.data_type.value_range();
+        let nodata = configs.nodata;
+        if nodata.fract() == 0f64 && nodata >= min_value && nodata <= max_value {
+            t
+        } else if t.size() >= 4 {
+            ZarrType::F64
+        } else {
+            ZarrType::F32
+        }
+    }
+
+    fn decode(self, b: &[u8], big_endian: bool) -> f64 {
+        macro_rules! decode {
+            ($t:ty, $n:expr) => {{
+                let mut a = [0u8; $n];
+                a.copy_from_slice(&b[..$n]);
+                if big_endian {
+                    <$t>::from_be_bytes(a) as f64
+                } else {
+                    <$t>::from_le_bytes(a) as f64
+                }
+            }};
+        }
+        match self {
+            ZarrType::Bool | ZarrType::U8 => b[0] as f64,
+            ZarrType::I8 => b[0] as i8 as f64,
+            ZarrType::I16 => decode!(i16, 2),
+            ZarrType::I32 => decode!(i32, 4),
+            ZarrType::I64 => decode!(i64, 8),
+            ZarrType::U16 => decode!(u16, 2),
+            ZarrType::U32 => decode!(u32, 4),
+            ZarrType::U64 => decode!(u64, 8),
+            ZarrType::F32 => decode!(f32, 4),
+            ZarrType::F64 => decode!(f64, 8),
+        }
+    }
+
+    /// Appends a value to `bytes`, in little-endian order.
+    fn encode(self, v: f64, bytes: &mut Vec<u8>) {
+        match self {
+            ZarrType::Bool => bytes.push((v != 0f64) as u8),
+            ZarrType::I8 => bytes.push(v as i8 as u8),
+            ZarrType::U8 => bytes.push(v as u8),
+            ZarrType::I16 => bytes.extend_from_slice(&(v as i16).to_le_bytes()),
+            ZarrType::I32 => bytes.extend_from_slice(&(v as i32).to_le_bytes()),
+            ZarrType::I64 => bytes.extend_from_slice(&(v as i64).to_le_bytes()),
+            ZarrType::U16 => bytes.extend_from_slice(&(v as u16).to_le_bytes()),
+            ZarrType::U32 => bytes.extend_from_slice(&(v as u32).to_le_bytes()),
+            ZarrType::U64 => bytes.extend_from_slice(&(v as u64).to_le_bytes()),
+            ZarrType::F32 => bytes.extend_from_slice(&(v as f32).to_le_bytes()),
+            ZarrType::F64 => bytes.extend_from_slice(&v.to_le_bytes()),
+        }
+    }
+}
+
+/// A step of the encoding of the chunks of an array. Version 2 compressors are represented by
+/// the equivalent version 3 codecs.
+#[derive(Clone)]
+enum Codec {
+    /// Reorders the dimensions of a chunk, such that the i'th stored dimension is dimension
+    /// `order[i]`; an empty order reverses the dimensions (i.e. Fortran order).
+    Transpose(Vec<usize>),
+    Bytes {
+        big_endian: bool,
+    },
+    Sharding {
+        chunk_shape: Vec<usize>,
+        codecs: Vec<Codec>,
+        index_codecs: Vec<Codec>,
+        index_at_end: bool,
+    },
+    Blosc,
+    Crc32c,
+    Gzip,
+    Lz4,
+    Zlib,
+    Zstd,
+}
+
+fn unsupported_codec(name: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("The Zarr codec '{}' is not supported.", name),
+    )
+}
+
+fn parse_v2_compressor(compressor: &Value) -> Result<Option<Codec>, Error> {
+    if compressor.is_null() {
+        return Ok(None);
+    }
+    let id = compressor["id"].as_str().unwrap_or_default();
+    Ok(Some(match id {
+        "blosc" => Codec::Blosc,
+        "gzip" => Codec::Gzip,
+        "lz4" => Codec::Lz4,
+        "zlib" => Codec::Zlib,
+        "zstd" => Codec::Zstd,
+        _ => return Err(unsupported_codec(id)),
+    }))
+}
+
+fn parse_v3_codecs(codecs: &Value) -> Result<Vec<Codec>, Error> {
+    let mut parsed = vec![];
+    for c in codecs.as_array().into_iter().flatten() {
+        let name = c["name"]
+            .as_str()
+            .or_else(|| c.as_str())
+            .unwrap_or_default();
+        let conf = &c["configuration"];
+        let codec = match name.trim_start_matches("numcodecs.") {
+            "transpose" => match &conf["order"] {
+                Value::Array(order) => Codec::Transpose(
+                    order
+                        .iter()
+                        .filter_map(|v| v.as_u64())
+                        .map(|v| v as usize)
+                        .collect(),
+                ),
+                Value::String(s) if s == "F" => Codec::Transpose(vec![]),
+                _ => continue,
+            },
+            "bytes" | "endian" => Codec::Bytes {
+                big_endian: conf["endian"].as_str() == Some("big"),
+            },
+            "sharding_indexed" => Codec::Sharding {
+                chunk_shape: conf["chunk_shape"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|v| v.as_u64())
+                    .map(|v| v as usize)
+                    .collect(),
+                codecs: parse_v3_codecs(&conf["codecs"])?,
+                index_codecs: parse_v3_codecs(&conf["index_codecs"])?,
+                index_at_end: conf["index_location"].as_str() != Some("start"),
+            },
+            "blosc" => Codec::Blosc,
+            "crc32c" => Codec::Crc32c,
+            "gzip" => Codec::Gzip,
+            "lz4" => Codec::Lz4,
+            "zlib" => Codec::Zlib,
+            "zstd" => Codec::Zstd,
+            _ => return Err(unsupported_codec(name)),
+        };
+        parsed.push(codec);
+    }
+    Ok(parsed)
+}
+
+/// The metadata of an array of a store.
+#[derive(Clone)]
+struct ZarrArray {
+    /// The path of the array within the store, which is empty for an array at its root.
+    path: String,
+    version: u8,
+    shape: Vec<usize>,
+    chunks: Vec<usize>,
+    dtype: ZarrType,
+    fill_value: f64,
+    codecs: Vec<Codec>,
+    /// Whether chunk keys begin with 'c', as do the default keys of version 3 arrays.
+    key_prefix: bool,
+    separator: String,
+    dims: Vec<String>,
+    attributes: Map<String, Value>,
+}
+
+fn usize_list(v: &Value) -> Vec<usize> {
+    v.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_u64())
+        .map(|v| v as usize)
+        .collect()
+}
+
+impl ZarrArray {
+    fn from_v2(
+        path: &str,
+        meta: &Value,
+        attributes: Map<String, Value>,
+    ) -> Result<ZarrArray, Error> {
+        let dtype_name = meta["dtype"].as_str().unwrap_or_default();
+        let (dtype, big_endian) = ZarrType::from_v2(dtype_name).ok_or_else(|| {
+            invalid(&format!(
+                "The data type ({}) of the Zarr array '{}' is not supported.",
+                dtype_name, path
+            ))
+        })?;
+        if meta["filters"].as_array().is_some_and(|f| !f.is_empty()) {
+            return Err(invalid(&format!(
+                "The Zarr array '{}' uses filters, which are not supported.",
+                path
+            )));
+        }
+        let mut codecs = vec![];
+        if meta["order"].as_str() == Some("F") {
+            codecs.push(Codec::Transpose(vec![]));
+        }
+        codecs.push(Codec::Bytes { big_endian });
+        if let Some(c) = parse_v2_compressor(&meta["compressor"])? {
+            codecs.push(c);
+        }
+        let dims = attributes
+            .get("_ARRAY_DIMENSIONS")
+            .and_then(|d| d.as_array())
+            .map(|d| {
+                d.iter()
+                    .map(|v| v.as_str().unwrap_or_default().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(ZarrArray {
+            path: path.to_string(),
+            version: 2,
+            shape: usize_list(&meta["shape"]),
+            chunks: usize_list(&meta["chunks"]),
+            dtype,
+            fill_value: json_number(&meta["fill_value"]).unwrap_or(f64::NAN),
+            codecs,
+            key_prefix: false,
+            separator: meta["dimension_separator"]
+                .as_str()
+                .unwrap_or(".")
+                .to_string(),
+            dims,
+            attributes,
+        })
+    }
+
+    fn from_v3(path: &str, meta: &Value) -> Result<ZarrArray, Error> {
+        let dtype_name = meta["data_type"].as_str().unwrap_or_default();
+        let dtype = ZarrType::from_v3(dtype_name).ok_or_else(|| {
+            invalid(&format!(
+                "The data type ({}) of the Zarr array '{}' is not supported.",
+                dtype_name, path
+            ))
+        })?;
+        if meta["chunk_grid"]["name"].as_str().unwrap_or("regular") != "regular" {
+            return Err(invalid(&format!(
+                "The Zarr array '{}' has an irregular chunk grid, which is not supported.",
+                path
+            )));
+        }
+        let fill_value = match &meta["fill_value"] {
+            // the bits of a float, e.g. '0x7fc00000'
+            Value::String(s) if s.starts_with("0x") => {
+                let bits = u64::from_str_radix(&s[2..], 16).unwrap_or(0);
+                match dtype {
+                    ZarrType::F32 => f32::from_bits(bits as u32) as f64,
+                    _ => f64::from_bits(bits),
+                }
+            }
+            v => json_number(v).unwrap_or(f64::NAN),
+        };
+        let encoding = &meta["chunk_key_encoding"];
+        let is_default = encoding["name"].as_str().unwrap_or("default") == "default";
+        let separator = encoding["configuration"]["separator"]
+            .as_str()
+            .unwrap_or(if is_default { "/" } else { "." })
+            .to_string();
+        Ok(ZarrArray {
+            path: path.to_string(),
+            version: 3,
+            shape: usize_list(&meta["shape"]),
+            chunks: usize_list(&meta["chunk_grid"]["configuration"]["chunk_shape"]),
+            dtype,
+            fill_value,
+            codecs: parse_v3_codecs(&meta["codecs"])?,
+            key_prefix: is_default,
+            separator,
+            dims: meta["dimension_names"]
+                .as_array()
+                .map(|d| {
+                    d.iter()
+                        .map(|v| v.as_str().unwrap_or_default().to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            attributes: meta["attributes"].as_object().cloned().unwrap_or_default(),
+        })
+    }
+
+    /// Returns the name of the array, i.e. the last component of its path.
+    fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or_default()
+    }
+
+    /// Returns the key of a chunk, relative to the root of the store.
+    fn chunk_key(&self, index: &[usize]) -> String {
+        let mut parts: Vec<String> = index.iter().map(|i| i.to_string()).collect();
+        if self.key_prefix {
+            parts.insert(0, "c".to_string());
+        } else if parts.is_empty() {
+            parts.push("0".to_string());
+        }
+        let key = parts.join(&self.separator);
+        if self.path.is_empty() {
+            key
+        } else {
+            format!("{}/{}", self.path, key)
+        }
+    }
+
+    fn number_attribute(&self, name: &str) -> Option<f64> {
+        self.attributes.get(name).and_then(json_number)
+    }
+
+    fn text_attribute(&self, name: &str) -> Option<String> {
+        match self.attributes.get(name)? {
+            Value::String(s) => Some(s.clone()),
+            Value::Null => None,
+            v => Some(v.to_string()),
+        }
+    }
+}
+
+/// The location of a store, i.e. a local directory or a prefix within an object store.
+#[derive(Clone)]
+struct Store {
+    root: String,
+    remote: bool,
+}
+
+impl Store {
+    fn new(file_name: &str) -> Store {
+        let root = file_name.trim().trim_end_matches(['/', '\\']).to_string();
+        Store {
+            remote: is_url(&root),
+            root,
+        }
+    }
+
+    fn location(&self, key: &str) -> String {
+        if self.remote {
+            format!("{}/{}", self.root, key)
+        } else {
+            Path::new(&self.root)
+                .join(key)
+                .to_string_lossy()
+                .to_string()
+        }
+    }
+
+    /// Reads the value of a key, which is None if the key doesn't exist.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        let location = self.location(key);
+        let result = if self.remote {
+            read_input(&location)
+        } else {
+            fs::read(&location)
+        };
+        match result {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_json(&self, key: &str) -> Result<Option<Value>, Error> {
+        match self.get(key)? {
+            Some(bytes) => serde_json::from_slice(&bytes).map(Some).map_err(|e| {
+                invalid(&format!(
+                    "The Zarr metadata {} could not be read: {}",
+                    self.location(key),
+                    e
+                ))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Writes the value of a key of a local store.
+    fn put(&self, key: &str, bytes: &[u8]) -> Result<(), Error> {
+        let location = self.location(key);
+        if let Some(parent) = Path::new(&location).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&location, bytes)
+    }
+
+    /// Lists the members (i.e. subdirectories) of a local group.
+    fn list_members(&self) -> Result<Vec<String>, Error> {
+        if self.remote {
+            return Err(invalid(&format!(
+                "The Zarr group {} has no consolidated metadata, which is needed to find its arrays in an object store.",
+                self.root
+            )));
+        }
+        let mut members = vec![];
+        for entry in fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                members.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        Ok(members)
+    }
+}
+
+/// Reads the metadata of the arrays of a store, i.e. of the array at its root, or of the arrays
+/// of its root group.
+fn read_arrays(store: &Store) -> Result<Vec<ZarrArray>, Error> {
+    let mut arrays = vec![];
+    if let Some(meta) = store.get_json("zarr.json")? {
+        if meta["node_type"].as_str() == Some("array") {
+            arrays.push(ZarrArray::from_v3("", &meta)?);
+        } else if let Some(members) = meta["consolidated_metadata"]["metadata"].as_object() {
+            for (path, m) in members {
+                if m["node_type"].as_str() == Some("array") {
+                    arrays.push(ZarrArray::from_v3(path, m)?);
+                }
+            }
+        } else {
+            for path in store.list_members()? {
+                if let Some(m) = store.get_json(&format!("{}/zarr.json", path))? {
+                    if m["node_type"].as_str() == Some("array") {
+                        arrays.push(ZarrArray::from_v3(&path, &m)?);
+                    }
+                }
+            }
+        }
+    } else if let Some(meta) = store.get_json(".zarray")? {
+        let attributes = store
+            .get_json(".zattrs")?
+            .and_then(|a| a.as_object().cloned());
+        arrays.push(ZarrArray::from_v2(
+            "",
+            &meta,
+            attributes.unwrap_or_default(),
+        )?);
+    } else if let Some(consolidated) = store.get_json(".zmetadata")? {
+        let metadata = consolidated["metadata"]
+            .as_object()
+            .cloned()
+            .unwrap_or_default();
+        for (key, m) in &metadata {
+            if let Some(path) = key.strip_suffix("/.zarray") {
+                let attributes = metadata
+                    .get(&format!("{}/.zattrs", path))
+                    .and_then(|a| a.as_object().cloned());
+                arrays.push(ZarrArray::from_v2(path, m, attributes.unwrap_or_default())?);
+            }
+        }
+    } else if store.get(".zgroup")?.is_some() {
+        for path in store.list_members()? {
+            if let Some(m) = store.get_json(&format!("{}/.zarray", path))? {
+                let attributes = store
+                    .get_json(&format!("{}/.zattrs", path))?
+                    .and_then(|a| a.as_object().cloned());
+                arrays.push(ZarrArray::from_v2(
+                    &path,
+                    &m,
+                    attributes.unwrap_or_default(),
+                )?);
+            }
+        }
+    } else {
+        return Err(Error::new(
+            ErrorKind::NotFound,
+            format!(
+                "{} is not a Zarr store; it has no zarr.json, .zarray, or .zgroup metadata.",
+                store.root
+            ),
+        ));
+    }
+    arrays.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(arrays)
+}
+
+/// Selects the array that is read as a raster, i.e. the first with two or more dimensions that
+/// isn't a coordinate array.
+fn select_array(arrays: &[ZarrArray]) -> Option<usize> {
+    let coordinates: Vec<String> = arrays
+        .iter()
+        .filter_map(|a| a.text_attribute("coordinates"))
+        .flat_map(|c| {
+            c.split_whitespace()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>()
+        })
+        .collect();
+    let candidates: Vec<usize> = (0..arrays.len())
+        .filter(|&i| arrays[i].shape.len() >= 2 && arrays[i].chunks.len() == arrays[i].shape.len())
+        .collect();
+    candidates
+        .iter()
+        .copied()
+        .find(|&i| {
+            let name = arrays[i].name().to_lowercase();
+            !coordinates.contains(&arrays[i].name().to_string())
+                && !["x", "y", "lon", "lat", "longitude", "latitude"].contains(&name.as_str())
+        })
+        .or_else(|| candidates.first().copied())
+}
+
+/// Decompresses a Blosc (version 1 format) buffer.
+fn blosc_decompress(src: &[u8]) -> Result<Vec<u8>, Error> {
+    let u32_at = |pos: usize| -> Result<usize, Error> {
+        src.get(pos..pos + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+            .ok_or_else(|| invalid("A Blosc-compressed Zarr chunk is truncated."))
+    };
+    if src.len() < BLOSC_HEADER_LEN {
+        return Err(invalid("A Blosc-compressed Zarr chunk is truncated."));
+    }
+    let flags = src[2];
+    let typesize = (src[3] as usize).max(1);
+    let nbytes = u32_at(4)?;
+    let blocksize = u32_at(8)?;
+    if flags & BLOSC_MEMCPYED != 0 {
+        return src
+            .get(BLOSC_HEADER_LEN..BLOSC_HEADER_LEN + nbytes)
+            .map(|b| b.to_vec())
+            .ok_or_else(|| invalid("A Blosc-compressed Zarr chunk is truncated."));
+    }
+    if flags & BLOSC_DOBITSHUFFLE != 0 {
+        return Err(invalid(
+            "Bit-shuffled Blosc-compressed Zarr chunks are not supported.",
+        ));
+    }
+    if nbytes == 0 || blocksize == 0 {
+        return Ok(vec![]);
+    }
+    let compcode = (flags >> 5) & 0x7;
+    let num_blocks = nbytes.div_ceil(blocksize);
+    let mut out = Vec::with_capacity(nbytes);
+    for b in 0..num_blocks {
+        let leftover = b == num_blocks - 1 && nbytes % blocksize != 0;
+        let block_len = if leftover {
+            nbytes % blocksize
+        } else {
+            blocksize
+        };
+        let num_splits = if flags & BLOSC_NOSPLIT == 0
+            && !leftover
+            && typesize <= BLOSC_MAX_SPLITS
+            && blocksize / typesize >= BLOSC_MIN_BUFFERSIZE
+        {
+            typesize
+        } else {
+            1
+        };
+        let split_len = block_len / num_splits;
+        let mut block = Vec::with_capacity(block_len);
+        let mut pos = u32_at(BLOSC_HEADER_LEN + 4 * b)?;
+        for _ in 0..num_splits {
+            let compressed_len = u32_at(pos)?;
+            pos += 4;
+            let stream = src
+                .get(pos..pos + compressed_len)
+                .ok_or_else(|| invalid("A Blosc-compressed Zarr chunk is truncated."))?;
+            if compressed_len == split_len {
+                block.extend_from_slice(stream);
+            } else {
+                let decompressed = match compcode {
+                    1 => lz4_flex::block::decompress(stream, split_len)
+                        .map_err(|_| invalid("A Blosc-compressed Zarr chunk could not be decompressed."))?,
+                    3 => decompress_to_vec_zlib(stream)
+                        .map_err(|_| invalid("A Blosc-compressed Zarr chunk could not be inflated."))?,
+                    4 => zstd_codec::decompress(stream, split_len)?,
+                    _ => {
+                        return Err(invalid(
+                            "Only the lz4, zlib, and zstd codecs of Blosc-compressed Zarr chunks are supported.",
+                        ))
+                    }
+                };
+                block.extend_from_slice(&decompressed);
+            }
+            pos += compressed_len;
+        }
+        if flags & BLOSC_DOSHUFFLE != 0 && typesize > 1 {
+            let n = block_len / typesize;
+            let mut unshuffled = block.clone();
+            for i in 0..n {
+                for j in 0..typesize {
+                    unshuffled[i * typesize + j] = block[j * n + i];
+                }
+            }
+            block = unshuffled;
+        }
+        out.extend_from_slice(&block);
+    }
+    Ok(out)
+}
+
+/// Decompresses a gzip stream.
+fn gunzip(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    if bytes.len() < 18 || bytes[0] != 0x1f || bytes[1] != 0x8b || bytes[2] != 8 {
+        return Err(invalid(
+            "A gzip-compressed Zarr chunk is not a gzip stream.",
+        ));
+    }
+    let flags = bytes[3];
+    let mut pos = 10;
+    if flags & 0x04 != 0 {
+        pos += 2 + u16::from_le_bytes([bytes[pos], bytes[pos + 1]]) as usize;
+    }
+    for flag in [0x08, 0x10] {
+        if flags & flag != 0 {
+            while pos < bytes.len() && bytes[pos] != 0 {
+                pos += 1;
+            }
+            pos += 1;
+        }
+    }
+    if flags & 0x02 != 0 {
+        pos += 2;
+    }
+    decompress_to_vec(bytes.get(pos..).unwrap_or_default())
+        .map_err(|_| invalid("A gzip-compressed Zarr chunk could not be inflated."))
+}
+
+/// Decodes a chunk with a bytes-to-bytes codec.
+fn decode_bytes(codec: &Codec, bytes: Vec<u8>) -> Result<Vec<u8>, Error> {
+    match codec {
+        Codec::Blosc => blosc_decompress(&bytes),
+        Codec::Crc32c => Ok(bytes[..bytes.len().saturating_sub(4)].to_vec()),
+        Codec::Gzip => gunzip(&bytes),
+        Codec::Lz4 => {
+            // numcodecs prefixes the LZ4 block with its uncompressed size
+            if bytes.len() < 4 {
+                return Err(invalid("An lz4-compressed Zarr chunk is truncated."));
+            }
+            let size = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+            lz4_flex::block::decompress(&bytes[4..], size)
+                .map_err(|_| invalid("An lz4-compressed Zarr chunk could not be decompressed."))
+        }
+        Codec::Zlib => decompress_to_vec_zlib(&bytes)
+            .map_err(|_| invalid("A zlib-compressed Zarr chunk could not be inflated.")),
+        Codec::Zstd => zstd_codec::decode_all(&bytes),
+        _ => Err(invalid("The codecs of a Zarr array are out of order.")),
+    }
+}
+
+/// Copies a block of values, in C order, into a larger array at the position `origin`.
+fn copy_block(
+    dst: &mut [f64],
+    dst_shape: &[usize],
+    src: &[f64],
+    src_shape: &[usize],
+    origin: &[usize],
+) {
+    let rank = src_shape.len();
+    let row_len = src_shape[rank - 1].min(dst_shape[rank - 1].saturating_sub(origin[rank - 1]));
+    let num_rows: usize = src_shape[..rank - 1].iter().product();
+    'rows: for r in 0..num_rows {
+        // the position of the row in the destination
+        let mut remainder = r;
+        let mut stride = dst_shape[rank - 1];
+        let mut dst_index = origin[rank - 1];
+        for d in (0..rank - 1).rev() {
+            let i = remainder % src_shape[d] + origin[d];
+            remainder /= src_shape[d];
+            if i >= dst_shape[d] {
+                continue 'rows;
+            }
+            dst_index += i * stride;
+            stride *= dst_shape[d];
+        }
+        let offset = r * src_shape[rank - 1];
+        dst[dst_index..dst_index + row_len].copy_from_slice(&src[offset..offset + row_len]);
+    }
+}
+
+/// Reorders values in C order over a transposed (stored) shape into C order over the shape of
+/// the chunk, where the i'th stored dimension is dimension `order[i]`.
+fn untranspose(values: &[f64], shape: &[usize], order: &[usize]) -> Vec<f64> {
+    let rank = shape.len();
+    let stored_shape: Vec<usize> = order.iter().map(|&i| shape[i]).collect();
+    // the stride in the stored values of each dimension of the chunk
+    let mut strides = vec![0usize; rank];
+    let mut stride = 1usize;
+    for i in (0..rank).rev() {
+        strides[order[i]] = stride;
+        stride *= stored_shape[i];
+    }
+    let mut out = Vec::with_capacity(values.len());
+    let mut index = vec![0usize; rank];
+    for _ in 0..values.len() {
+        out.push(values[(0..rank).map(|d| index[d] * strides[d]).sum::<usize>()]);
+        for d in (0..rank).rev() {
+            index[d] += 1;
+            if index[d] < shape[d] {
+                break;
+            }
+            index[d] = 0;
+        }
+    }
+    out
+}
+
+/// Decodes an encoded chunk of the given shape, returning its values in C order.
+fn decode_chunk(
+    codecs: &[Codec],
+    mut bytes: Vec<u8>,
+    shape: &[usize],
+    dtype: ZarrType,
+    fill_value: f64,
+) -> Result<Vec<f64>, Error> {
+    let position = codecs
+        .iter()
+        .position(|c| matches!(c, Codec::Bytes { .. } | Codec::Sharding { .. }))
+        .unwrap_or(codecs.len());
+    let mut orders = vec![];
+    let mut stored_shape = shape.to_vec();
+    for c in &codecs[..position] {
+        if let Codec::Transpose(order) = c {
+            let rank = stored_shape.len();
+            let order: Vec<usize> = if order.is_empty() {
+                (0..rank).rev().collect()
+            } else {
+                order.clone()
+            };
+            if order.len() != rank || order.iter().any(|&i| i >= rank) {
+                return Err(invalid("A Zarr array has an invalid transpose order."));
+            }
+            orders.push((stored_shape.clone(), order.clone()));
+            stored_shape = order.iter().map(|&i| stored_shape[i]).collect();
+        }
+    }
+    if position + 1 < codecs.len() {
+        for c in codecs[position + 1..].iter().rev() {
+            bytes = decode_bytes(c, bytes)?;
+        }
+    }
+    let num_values: usize = stored_shape.iter().product();
+    let mut values = match codecs.get(position) {
+        Some(sharding @ Codec::Sharding { .. }) => {
+            decode_shard(sharding, &bytes, &stored_shape, dtype, fill_value)?
+        }
+        other => {
+            let big_endian = matches!(other, Some(Codec::Bytes { big_endian: true }));
+            let size = dtype.size();
+            if bytes.len() < num_values * size {
+                return Err(invalid("A Zarr chunk is smaller than its shape."));
+            }
+            (0..num_values)
+                .map(|i| dtype.decode(&bytes[i * size..], big_endian))
+                .collect()
+        }
+    };
+    for (shape, order) in orders.iter().rev() {
+        values = untranspose(&values, shape, order);
+    }
+    Ok(values)
+}
+
+/// Decodes a shard, i.e. a chunk made of inner chunks, which are located by an index of their
+/// offsets and lengths at the start or end of the shard.
+fn decode_shard(
+    sharding: &Codec,
+    bytes: &[u8],
+    shape: &[usize],
+    dtype: ZarrType,
+    fill_value: f64,
+) -> Result<Vec<f64>, Error> {
+    let (chunk_shape, codecs, index_codecs, index_at_end) = match sharding {
+        Codec::Sharding {
+            chunk_shape,
+            codecs,
+            index_codecs,
+            index_at_end,
+        } => (chunk_shape, codecs, index_codecs, *index_at_end),
+        _ => return Err(invalid("The codecs of a Zarr array are out of order.")),
+    };
+    if chunk_shape.len() != shape.len() || chunk_shape.contains(&0) {
+        return Err(invalid(
+            "A sharded Zarr array has an invalid inner chunk shape.",
+        ));
+    }
+    let grid: Vec<usize> = shape
+        .iter()
+        .zip(chunk_shape)
+        .map(|(s, c)| s.div_ceil(*c))
+        .collect();
+    let num_chunks: usize = grid.iter().product();
+    let checksum_len = if index_codecs.iter().any(|c| matches!(c, Codec::Crc32c)) {
+        4
+    } else {
+        0
+    };
+    let big_endian = index_codecs
+        .iter()
+        .any(|c| matches!(c, Codec::Bytes { big_endian: true }));
+    let index_len = 16 * num_chunks + checksum_len;
+    if bytes.len() < index_len {
+        return Err(invalid("A Zarr shard is smaller than its index."));
+    }
+    let index = if index_at_end {
+        &bytes[bytes.len() - index_len..]
+    } else {
+        &bytes[..index_len]
+    };
+    let read_u64 = |pos: usize| -> u64 {
+        let mut a = [0u8; 8];
+        a.copy_from_slice(&index[pos..pos + 8]);
+        if big_endian {
+            u64::from_be_bytes(a)
+        } else {
+            u64::from_le_bytes(a)
+        }
+    };
+    let mut values = vec![fill_value; shape.iter().product()];
+    for k in 0..num_chunks {
+        let (offset, length) = (read_u64(16 * k), read_u64(16 * k + 8));
+        if offset == u64::MAX && length == u64::MAX {
+            // an empty inner chunk
+            continue;
+        }
+        let (offset, length) = (offset as usize, length as usize);
+        let chunk = bytes
+            .get(offset..offset + length)
+            .ok_or_else(|| invalid("A Zarr shard is truncated."))?;
+        let chunk_values = decode_chunk(codecs, chunk.to_vec(), chunk_shape, dtype, fill_value)?;
+        let mut origin = vec![0usize; grid.len()];
+        let mut remainder = k;
+        for d in (0..grid.len()).rev() {
+            origin[d] = (remainder % grid[d]) * chunk_shape[d];
+            remainder /= grid[d];
+        }
+        copy_block(&mut values, shape, &chunk_values, chunk_shape, &origin);
+    }
+    Ok(values)
+}
+
+/// Reads and decodes a chunk of an array, returning its values in C order, or None if the chunk
+/// doesn't exist, in which case it holds the fill value.
+fn read_chunk(
+    store: &Store,
+    array: &ZarrArray,
+    index: &[usize],
+) -> Result<Option<Vec<f64>>, Error> {
+    match store.get(&array.chunk_key(index))? {
+        Some(bytes) => decode_chunk(
+            &array.codecs,
+            bytes,
+            &array.chunks,
+            array.dtype,
+            array.fill_value,
+        )
+        .map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Reads the values of a one-dimensional array, e.g. a coordinate array.
+fn read_vector(store: &Store, array: &ZarrArray) -> Result<Vec<f64>, Error> {
+    let (len, chunk_len) = (array.shape[0], array.chunks[0].max(1));
+    let mut values = Vec::with_capacity(len + chunk_len);
+    for k in 0..len.div_ceil(chunk_len) {
+        match read_chunk(store, array, &[k])? {
+            Some(v) => values.extend_from_slice(&v),
+            None => values.resize((k + 1) * chunk_len, array.fill_value),
+        }
+    }
+    values.truncate(len);
+    Ok(values)
+}
+
+pub fn read_zarr(
+    file_name: &String,
+    configs: &mut RasterConfigs,
+    data: &mut Vec<f64>,
+) -> Result<(), Error> {
+    let store = Store::new(file_name);
+    let arrays = read_arrays(&store)?;
+    let array = match select_array(&arrays) {
+        Some(i) => arrays[i].clone(),
+        None => {
+            return Err(invalid(&format!(
+                "The Zarr store {} does not contain an array with two or more dimensions.",
+                file_name
+            )))
+        }
+    };
+    let rank = array.shape.len();
+    let (rows, columns) = (array.shape[rank - 2], array.shape[rank - 1]);
+    if rows == 0 || columns == 0 {
+        return Err(invalid("The Zarr array is empty."));
+    }
+    if array.chunks.contains(&0) {
+        return Err(invalid("The Zarr array has an invalid chunk shape."));
+    }
+    configs.rows = rows;
+    configs.columns = columns;
+
+    // Select the band, i.e. the position along each of the leading dimensions.
+    let wb_configs = whitebox_common::configs::get_configs()?;
+    let leading = &array.shape[..rank - 2];
+    let num_bands: usize = leading.iter().product();
+    let band = wb_configs.netcdf_band.max(1);
+    if band > num_bands {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Band {} was specified, but the Zarr array '{}' has {} band(s).",
+                band,
+                array.name(),
+                num_bands
+            ),
+        ));
+    }
+    let mut position = vec![0usize; leading.len()];
+    let mut remainder = band - 1;
+    for i in (0..leading.len()).rev() {
+        position[i] = remainder % leading[i];
+        remainder /= leading[i];
+    }
+    // the index of the band's chunk along each leading dimension, and the band's offset within it
+    let mut leading_chunks = vec![];
+    let mut band_offset = 0usize;
+    for (p, c) in position.iter().zip(&array.chunks) {
+        leading_chunks.push(p / c);
+        band_offset = band_offset * c + p % c;
+    }
+
+    let scale = array.number_attribute("scale_factor").unwrap_or(1f64);
+    let offset = array.number_attribute("add_offset").unwrap_or(0f64);
+    let is_packed = scale != 1f64 || offset != 0f64;
+    let fill = array
+        .number_attribute("_FillValue")
+        .unwrap_or(array.fill_value);
+    let missing = array.number_attribute("missing_value");
+    configs.nodata = if !is_packed && fill.is_finite() {
+        fill
+    } else {
+        -32768f64
+    };
+    let nodata = configs.nodata;
+    let is_missing = move |v: f64| {
+        v.is_nan()
+            || v == fill
+            || (array.dtype == ZarrType::F32 && v as f32 == fill as f32)
+            || missing == Some(v)
+    };
+
+    // Read the chunks of the band, in parallel.
+    let (chunk_rows, chunk_columns) = (array.chunks[rank - 2], array.chunks[rank - 1]);
+    let num_chunk_rows = rows.div_ceil(chunk_rows);
+    let num_chunk_columns = columns.div_ceil(chunk_columns);
+    let num_chunks = num_chunk_rows * num_chunk_columns;
+    let num_procs = if store.remote {
+        REMOTE_REQUESTS
+    } else {
+        whitebox_common::utils::ThreadPool::from_settings().num_threads()
+    }
+    .min(num_chunks)
+    .max(1);
+    let shared_store = Arc::new(store.clone());
+    let shared_array = Arc::new(array.clone());
+    let leading_chunks = Arc::new(leading_chunks);
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let store = shared_store.clone();
+        let array = shared_array.clone();
+        let leading_chunks = leading_chunks.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            for k in (0..num_chunks).filter(|k| k % num_procs == tid) {
+                let mut index = (*leading_chunks).clone();
+                index.push(k / num_chunk_columns);
+                index.push(k % num_chunk_columns);
+                let result = read_chunk(&store, &array, &index);
+                let failed = result.is_err();
+                if tx.send((k, result)).is_err() || failed {
+                    break;
+                }
+            }
+        });
+    }
+    data.clear();
+    data.resize(rows * columns, nodata);
+    let fill_cell = if is_missing(array.fill_value) {
+        nodata
+    } else {
+        array.fill_value * scale + offset
+    };
+    for _ in 0..num_chunks {
+        let (k, result) = rx.recv().expect("Error receiving data from thread.");
+        let values = result?;
+        let (row0, col0) = (
+            (k / num_chunk_columns) * chunk_rows,
+            (k % num_chunk_columns) * chunk_columns,
+        );
+        for r in 0..chunk_rows.min(rows - row0) {
+            let row = row0 + r;
+            for c in 0..chunk_columns.min(columns - col0) {
+                data[row * columns + col0 + c] = match &values {
+                    Some(values) => {
+                        let v = values[(band_offset * chunk_rows + r) * chunk_columns + c];
+                        if is_missing(v) {
+                            nodata
+                        } else {
+                            v * scale + offset
+                        }
+                    }
+                    None => fill_cell,
+                };
+            }
+        }
+    }
+
+    configs.data_type = if is_packed {
+        DataType::F32
+    } else {
+        array.dtype.data_type()
+    };
+    let (min_value, max_value) = configs.data_type.value_range();
+    if configs.data_type.is_integer()
+        && (nodata.fract() != 0f64 || nodata < min_value || nodata > max_value)
+    {
+        // the nodata value can't be represented by the type of the array
+        configs.data_type = DataType::F32;
+    }
+
+    // Georeference the grid, from its coordinate arrays or the GeoTransform of its grid mapping.
+    let coordinate_array = |dim: usize, len: usize| -> Option<&ZarrArray> {
+        let name = array.dims.get(dim)?;
+        arrays.iter().find(|a| {
+            !name.is_empty() && a.name() == name && a.shape == vec![len] && a.chunks.len() == 1
+        })
+    };
+    let x_coords = match coordinate_array(rank - 1, columns) {
+        Some(a) => Some((
+            read_vector(&store, a)?,
+            a.text_attribute("units").unwrap_or_default(),
+        )),
+        None => None,
+    };
+    let y_coords = match coordinate_array(rank - 2, rows) {
+        Some(a) => Some(read_vector(&store, a)?),
+        None => None,
+    };
+    let flip = match &y_coords {
+        Some(y) => y.len() > 1 && y[y.len() - 1] > y[0],
+        None => false,
+    };
+    if flip {
+        for row in 0..rows / 2 {
+            for column in 0..columns {
+                data.swap(row * columns + column, (rows - 1 - row) * columns + column);
+            }
+        }
+    }
+    let grid_mapping = array
+        .text_attribute("grid_mapping")
+        .and_then(|name| arrays.iter().find(|a| a.name() == name));
+    let geo_transform: Vec<f64> =
+        match grid_mapping.and_then(|gm| gm.attributes.get("GeoTransform")) {
+            Some(Value::String(s)) => s
+                .split_whitespace()
+                .filter_map(|v| v.parse::<f64>().ok())
+                .collect(),
+            Some(Value::Array(a)) => a.iter().filter_map(|v| v.as_f64()).collect(),
+            _ => vec![],
+        };
+    match (&x_coords, &y_coords) {
+        (Some((x, x_units)), Some(y)) => {
+            configs.resolution_x = if columns > 1 {
+                ((x[columns - 1] - x[0]) / (columns - 1) as f64).abs()
+            } else if geo_transform.len() == 6 {
+                geo_transform[1].abs()
+            } else {
+                1f64
+            };
+            configs.resolution_y = if rows > 1 {
+                ((y[rows - 1] - y[0]) / (rows - 1) as f64).abs()
+            } else if geo_transform.len() == 6 {
+                geo_transform[5].abs()
+            } else {
+                configs.resolution_x
+            };
+            let (x_min, y_max) = (
+                x.iter().cloned().fold(f64::INFINITY, f64::min),
+                y.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            );
+            configs.west = x_min - configs.resolution_x / 2f64;
+            configs.north = y_max + configs.resolution_y / 2f64;
+            let x_units = x_units.to_lowercase();
+            if x_units.starts_with("degree") {
+                configs.xy_units = "degrees".to_string();
+            } else if x_units == "m" || x_units.starts_with("met") {
+                configs.xy_units = "metres".to_string();
+            } else if is_specified(&x_units) {
+                configs.xy_units = x_units;
+            }
+        }
+        _ if geo_transform.len() == 6 => {
+            configs.west = geo_transform[0];
+            configs.resolution_x = geo_transform[1].abs();
+            configs.north = geo_transform[3];
+            configs.resolution_y = geo_transform[5].abs();
+            if geo_transform[5] > 0f64 {
+                // a bottom-up GeoTransform, whose origin is the south-west corner
+                configs.north = geo_transform[3] + rows as f64 * geo_transform[5];
+            }
+        }
+        _ => {
+            configs.west = 0f64;
+            configs.north = rows as f64;
+            configs.resolution_x = 1f64;
+            configs.resolution_y = 1f64;
+        }
+    }
+    configs.east = configs.west + columns as f64 * configs.resolution_x;
+    configs.south = configs.north - rows as f64 * configs.resolution_y;
+    configs.pixel_is_area = true;
+
+    let mut wkt = String::new();
+    if let Some(gm) = grid_mapping {
+        wkt = gm
+            .text_attribute("crs_wkt")
+            .or_else(|| gm.text_attribute("spatial_ref"))
+            .unwrap_or_default();
+        if let Some(epsg) = gm.text_attribute("epsg_code") {
+            configs.epsg_code = epsg
+                .to_uppercase()
+                .trim_start_matches("EPSG:")
+                .trim()
+                .parse::<u16>()
+                .unwrap_or(0);
+        }
+        if configs.epsg_code == 0
+            && !is_specified(&wkt)
+            && gm.text_attribute("grid_mapping_name").unwrap_or_default() == "latitude_longitude"
+        {
+            configs.epsg_code = 4326;
+        }
+    } else if configs.xy_units == "degrees" && !is_specified(&wkt) {
+        configs.epsg_code = 4326;
+    }
+    if is_specified(&wkt) {
+        configs.coordinate_ref_system_wkt = wkt;
+    } else if configs.epsg_code > 0 {
+        let wkt = esri_wkt_from_epsg(configs.epsg_code);
+        if !wkt.starts_with("Unknown") {
+            configs.coordinate_ref_system_wkt = wkt;
+        }
+    }
+    if configs.epsg_code == 4326 {
+        configs.xy_units = "degrees".to_string();
+    }
+
+    configs.title = array
+        .text_attribute("long_name")
+        .unwrap_or(array.name().to_string());
+    if let Some(units) = array.text_attribute("units") {
+        configs.z_units = units;
+    }
+    if !array.path.is_empty() {
+        configs.metadata.push(format!("Zarr array: {}", array.path));
+    }
+    if num_bands > 1 {
+        configs
+            .metadata
+            .push(format!("Zarr band: {} of {}", band, num_bands));
+    }
+    configs
+        .metadata
+        .push(format!("Zarr format: version {}", array.version));
+
+    Ok(())
+}
+
+/// Returns an array name, i.e. one made of letters, digits and underscores, that starts with a
+/// letter.
+fn array_name(short_name: &str) -> String {
+    let mut name: String = short_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name = format!("v_{}", name);
+    }
+    name
+}
+
+/// An array of a store that is being written.
+struct OutputArray {
+    name: String,
+    shape: Vec<usize>,
+    chunks: Vec<usize>,
+    dtype: ZarrType,
+    fill_value: f64,
+    dims: Vec<&'static str>,
+    attributes: Map<String, Value>,
+}
+
+impl OutputArray {
+    /// Returns the metadata documents of the array, keyed by their paths within the store.
+    fn metadata(&self, version: u8) -> Vec<(String, Value)> {
+        let compressed = !self.shape.is_empty();
+        if version == 3 {
+            let mut codecs = vec![json!({"name": "bytes", "configuration": {"endian": "little"}})];
+            if compressed {
+                codecs.push(json!({"name": "zstd", "configuration": {"level": ZSTD_LEVEL, "checksum": false}}));
+            }
+            let meta = json!({
+                "zarr_format": 3,
+                "node_type": "array",
+                "shape": self.shape,
+                "data_type": self.dtype.v3_name(),
+                "chunk_grid": {"name": "regular", "configuration": {"chunk_shape": self.chunks}},
+                "chunk_key_encoding": {"name": "default", "configuration": {"separator": "/"}},
+                "fill_value": fill_json(self.fill_value, self.dtype),
+                "codecs": codecs,
+                "attributes": self.attributes,
+                "dimension_names": self.dims,
+            });
+            vec![(format!("{}/zarr.json", self.name), meta)]
+        } else {
+            let meta = json!({
+                "zarr_format": 2,
+                "shape": self.shape,
+                "chunks": self.chunks,
+                "dtype": self.dtype.v2_name(),
+                "compressor": if compressed { json!({"id": "zstd", "level": ZSTD_LEVEL}) } else { Value::Null },
+                "fill_value": fill_json(self.fill_value, self.dtype),
+                "filters": Value::Null,
+                "order": "C",
+                "dimension_separator": ".",
+            });
+            let mut attributes = self.attributes.clone();
+            attributes.remove("_FillValue");
+            attributes.insert("_ARRAY_DIMENSIONS".to_string(), json!(self.dims));
+            vec![
+                (format!("{}/.zarray", self.name), meta),
+                (format!("{}/.zattrs", self.name), Value::Object(attributes)),
+            ]
+        }
+    }
+
+    /// Returns the key of a chunk of the array.
+    fn chunk_key(&self, version: u8, index: &[usize]) -> String {
+        let parts: Vec<String> = index.iter().map(|i| i.to_string()).collect();
+        if version == 3 {
+            format!("{}/c/{}", self.name, parts.join("/"))
+        } else {
+            format!("{}/{}", self.name, parts.join("."))
+        }
+    }
+}
+
+fn encode_chunk(values: &[f64], dtype: ZarrType) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(values.len() * dtype.size());
+    for &v in values {
+        dtype.encode(v, &mut bytes);
+    }
+    zstd_codec::compress(&bytes, ZSTD_LEVEL)
+}
+
+pub fn write_zarr(r: &mut Raster) -> Result<(), Error> {
+    if r.configs.data_type == DataType::RGB24
+        || r.configs.data_type == DataType::RGB48
+        || r.configs.data_type == DataType::RGBA32
+        || r.configs.photometric_interp == PhotometricInterpretation::RGB
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Colour composite rasters cannot be written as Zarr stores.",
+        ));
+    }
+    let version = match whitebox_common::configs::get_configs()?.zarr_version {
+        3 => 3u8,
+        _ => 2u8,
+    };
+
+    let (rows, columns) = (r.configs.rows, r.configs.columns);
+    let geographic = r.is_in_geographic_coordinates();
+    let (x_name, y_name) = if geographic {
+        ("lon", "lat")
+    } else {
+        ("x", "y")
+    };
+    let dtype = ZarrType::for_raster(&r.configs);
+    let nodata = r.configs.nodata;
+
+    let mut x_attributes = Map::new();
+    let mut y_attributes = Map::new();
+    if geographic {
+        x_attributes.insert("standard_name".to_string(), json!("longitude"));
+        x_attributes.insert("long_name".to_string(), json!("longitude"));
+        x_attributes.insert("units".to_string(), json!("degrees_east"));
+        y_attributes.insert("standard_name".to_string(), json!("latitude"));
+        y_attributes.insert("long_name".to_string(), json!("latitude"));
+        y_attributes.insert("units".to_string(), json!("degrees_north"));
+    } else {
+        x_attributes.insert(
+            "standard_name".to_string(),
+            json!("projection_x_coordinate"),
+        );
+        x_attributes.insert("long_name".to_string(), json!("x coordinate of projection"));
+        y_attributes.insert(
+            "standard_name".to_string(),
+            json!("projection_y_coordinate"),
+        );
+        y_attributes.insert("long_name".to_string(), json!("y coordinate of projection"));
+        let xy_units = r.configs.xy_units.to_lowercase();
+        let units = if xy_units.starts_with("met") || xy_units == "m" {
+            "m"
+        } else if xy_units.starts_with('f') {
+            "ft"
+        } else {
+            ""
+        };
+        if !units.is_empty() {
+            x_attributes.insert("units".to_string(), json!(units));
+            y_attributes.insert("units".to_string(), json!(units));
+        }
+    }
+    x_attributes.insert("axis".to_string(), json!("X"));
+    y_attributes.insert("axis".to_string(), json!("Y"));
+
+    let mut crs_attributes = Map::new();
+    if geographic {
+        crs_attributes.insert("grid_mapping_name".to_string(), json!("latitude_longitude"));
+    }
+    let wkt = if is_specified(&r.configs.coordinate_ref_system_wkt) {
+        r.configs.coordinate_ref_system_wkt.clone()
+    } else if r.configs.epsg_code > 0 {
+        match esri_wkt_from_epsg(r.configs.epsg_code) {
+            s if s.starts_with("Unknown") => String::new(),
+            s => s,
+        }
+    } else {
+        String::new()
+    };
+    if !wkt.is_empty() {
+        crs_attributes.insert("crs_wkt".to_string(), json!(wkt));
+        crs_attributes.insert("spatial_ref".to_string(), json!(wkt));
+    }
+    if r.configs.epsg_code > 0 {
+        crs_attributes.insert(
+            "epsg_code".to_string(),
+            json!(format!("EPSG:{}", r.configs.epsg_code)),
+        );
+    }
+    crs_attributes.insert(
+        "GeoTransform".to_string(),
+        json!(format!(
+            "{} {} 0 {} 0 {}",
+            r.configs.west, r.configs.resolution_x, r.configs.north, -r.configs.resolution_y
+        )),
+    );
+
+    let mut data_attributes = Map::new();
+    if !r.configs.title.is_empty() {
+        data_attributes.insert("long_name".to_string(), json!(r.configs.title));
+    }
+    if is_specified(&r.configs.z_units) {
+        data_attributes.insert("units".to_string(), json!(r.configs.z_units));
+    }
+    data_attributes.insert("_FillValue".to_string(), fill_json(nodata, dtype));
+    data_attributes.insert("grid_mapping".to_string(), json!("spatial_ref"));
+
+    let data_array = OutputArray {
+        name: array_name(&r.get_short_filename()),
+        shape: vec![rows, columns],
+        chunks: vec![CHUNK_SIZE.min(rows), CHUNK_SIZE.min(columns)],
+        dtype,
+        fill_value: nodata,
+        dims: vec![y_name, x_name],
+        attributes: data_attributes,
+    };
+    let y_array = OutputArray {
+        name: y_name.to_string(),
+        shape: vec![rows],
+        chunks: vec![rows],
+        dtype: ZarrType::F64,
+        fill_value: f64::NAN,
+        dims: vec![y_name],
+        attributes: y_attributes,
+    };
+    let x_array = OutputArray {
+        name: x_name.to_string(),
+        shape: vec![columns],
+        chunks: vec![columns],
+        dtype: ZarrType::F64,
+        fill_value: f64::NAN,
+        dims: vec![x_name],
+        attributes: x_attributes,
+    };
+    let crs_array = OutputArray {
+        name: "spatial_ref".to_string(),
+        shape: vec![],
+        chunks: vec![],
+        dtype: ZarrType::I32,
+        fill_value: 0f64,
+        dims: vec![],
+        attributes: crs_attributes,
+    };
+
+    fs::create_dir_all(&r.file_name)?;
+    let store = Store::new(&r.file_name);
+
+    // The coordinate arrays are each written as a single chunk.
+    let y_values: Vec<f64> = (0..rows)
+        .map(|row| r.get_y_from_row(row as isize))
+        .collect();
+    store.put(
+        &y_array.chunk_key(version, &[0]),
+        &encode_chunk(&y_values, ZarrType::F64)?,
+    )?;
+    let x_values: Vec<f64> = (0..columns)
+        .map(|column| r.get_x_from_column(column as isize))
+        .collect();
+    store.put(
+        &x_array.chunk_key(version, &[0]),
+        &encode_chunk(&x_values, ZarrType::F64)?,
+    )?;
+
+    // The chunks of the data are encoded and written in parallel.
+    let num_chunk_rows = rows.div_ceil(CHUNK_SIZE);
+    let num_chunk_columns = columns.div_ceil(CHUNK_SIZE);
+    let num_chunks = num_chunk_rows * num_chunk_columns;
+    let num_procs = whitebox_common::utils::ThreadPool::from_settings().max_threads(num_chunks).num_threads();
+    let (chunk_rows, chunk_columns) = (data_array.chunks[0], data_array.chunks[1]);
+    let data = Arc::new(std::mem::take(&mut r.data));
+    let mut handles = vec![];
+    for tid in 0..num_procs {
+        let data = data.clone();
+        let store = store.clone();
+        let keys: Vec<(usize, String)> = (0..num_chunks)
+            .filter(|k| k % num_procs == tid)
+            .map(|k| {
+                (
+                    k,
+                    data_array.chunk_key(version, &[k / num_chunk_columns, k % num_chunk_columns]),
+                )
+            })
+            .collect();
+        handles.push(spawn(move || -> Result<(), Error> {
+            let mut values = vec![nodata; chunk_rows * chunk_columns];
+            for (k, key) in keys {
+                let (row0, col0) = (
+                    (k / num_chunk_columns) * chunk_rows,
+                    (k % num_chunk_columns) * chunk_columns,
+                );
+                let mut is_empty = true;
+                for r in 0..chunk_rows {
+                    for c in 0..chunk_columns {
+                        let (row, column) = (row0 + r, col0 + c);
+                        let v = if row < rows && column < columns {
+                            data[row * columns + column]
+                        } else {
+                            nodata
+                        };
+                        if !is_nodata(v, nodata) {
+                            is_empty = false;
+                        }
+                        values[r * chunk_columns + c] = v;
+                    }
+                }
+                // chunks of nodata are left to the fill value
+                if !is_empty {
+                    store.put(&key, &encode_chunk(&values, dtype)?)?;
+                }
+            }
+            Ok(())
+        }));
+    }
+    let mut result = Ok(());
+    for h in handles {
+        let r = h.join().expect("Error joining a Zarr writing thread.");
+        if result.is_ok() {
+            result = r;
+        }
+    }
+    r.data = Arc::try_unwrap(data).unwrap_or_else(|d| (*d).clone());
+    result?;
+
+    // The metadata are written last, with the group's consolidated metadata.
+    let mut group_attributes = Map::new();
+    group_attributes.insert("Conventions".to_string(), json!("CF-1.8"));
+    if !r.configs.title.is_empty() {
+        group_attributes.insert("title".to_string(), json!(r.configs.title));
+    }
+    group_attributes.insert(
+        "history".to_string(),
+        json!(format!(
+            "{}: created by WhiteboxTools",
+            chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ")
+        )),
+    );
+    let mut consolidated = Map::new();
+    for a in [&data_array, &y_array, &x_array, &crs_array] {
+        for (key, meta) in a.metadata(version) {
+            store.put(&key, serde_json::to_string_pretty(&meta)?.as_bytes())?;
+            if version == 3 {
+                consolidated.insert(a.name.clone(), meta);
+            } else {
+                consolidated.insert(key, meta);
+            }
+        }
+    }
+    if version == 3 {
+        let group = json!({
+            "zarr_format": 3,
+            "node_type": "group",
+            "attributes": group_attributes,
+            "consolidated_metadata": {"kind": "inline", "must_understand": false, "metadata": consolidated},
+        });
+        store.put(
+            "zarr.json",
+            serde_json::to_string_pretty(&group)?.as_bytes(),
+        )?;
+    } else {
+        let zgroup = json!({"zarr_format": 2});
+        consolidated.insert(".zgroup".to_string(), zgroup.clone());
+        consolidated.insert(
+            ".zattrs".to_string(),
+            Value::Object(group_attributes.clone()),
+        );
+        store.put(".zgroup", serde_json::to_string_pretty(&zgroup)?.as_bytes())?;
+        store.put(
+            ".zattrs",
+            serde_json::to_string_pretty(&group_attributes)?.as_bytes(),
+        )?;
+        let zmetadata = json!({"metadata": consolidated, "zarr_consolidated_format": 1});
+        store.put(
+            ".zmetadata",
+            serde_json::to_string_pretty(&zmetadata)?.as_bytes(),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: ZSTD compression is provided by the zstd crate, which builds the C library, and so it is
+not available in wasm32 builds. There, reading or writing ZSTD-compressed data (GeoTIFF,
+Whitebox and Zarr rasters) fails with an error, while the other codecs are unaffected.
+*/
+
+use std::io::Error;
+#[cfg(target_arch = "wasm32")]
+use std::io::ErrorKind;
+
+/// Compresses a block at the given compression level.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn compress(bytes: &[u8], level: i32) -> Result<Vec<u8>, Error> {
+    zstd::bulk::compress(bytes, level)
+}
+
+/// Decompresses a block whose decompressed size is at most `capacity` bytes.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn decompress(bytes: &[u8], capacity: usize) -> Result<Vec<u8>, Error> {
+    zstd::bulk::decompress(bytes, capacity)
+}
+
+/// Decompresses a stream of one or more frames, of any size.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn decode_all(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    zstd::stream::decode_all(bytes)
+}
+
+/// Returns the largest size of the compressed form of `len` bytes.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn compress_bound(len: usize) -> usize {
+    zstd::zstd_safe::compress_bound(len)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn unsupported() -> Error {
+    Error::new(
+        ErrorKind::Unsupported,
+        "ZSTD compression is not supported by the wasm32 build of WhiteboxTools.",
+    )
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn compress(_bytes: &[u8], _level: i32) -> Result<Vec<u8>, Error> {
+    Err(unsupported())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn decompress(_bytes: &[u8], _capacity: usize) -> Result<Vec<u8>, Error> {
+    Err(unsupported())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn decode_all(_bytes: &[u8]) -> Result<Vec<u8>, Error> {
+    Err(unsupported())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn compress_bound(len: usize) -> usize {
+    // ZSTD_COMPRESSBOUND of the C library
+    len + (len >> 8) + if len < 128 << 10 { ((128 << 10) - len) >> 11 } else { 0 }
+}
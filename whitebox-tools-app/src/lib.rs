@@ -0,0 +1,126 @@
+/*
+This code is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+/*!
+The WhiteboxTools library. This crate builds both the `whitebox_tools` command-line program and a
+library containing its tools, so that they can be used from other Rust programs.
+
+Every tool can be run as it is from the command line, with string arguments and files on disk:
+
+```no_run
+use whitebox_tools::tools::gis_analysis::CostAllocation;
+use whitebox_tools::tools::{ConsoleProgress, WhiteboxTool};
+
+let args = vec![
+    "--source=sources.tif".to_string(),
+    "--backlink=backlink.tif".to_string(),
+    "--output=allocation.tif".to_string(),
+];
+CostAllocation::new().run(args, "/path/to/data/", false, &ConsoleProgress).unwrap();
+```
+
+In verbose mode, a tool sends its progress to the `ProgressReporter` that it is given, which may
+be used to display the progress of the tool, e.g. in a GUI:
+
+```no_run
+use whitebox_tools::tools::terrain_analysis::Slope;
+use whitebox_tools::tools::{ProgressReporter, WhiteboxTool};
+
+struct ProgressBar;
+
+impl ProgressReporter for ProgressBar {
+    fn progress(&self, label: &str, percent: usize) {
+        eprint!("\r{}: [{:<50}]", label, "#".repeat(percent / 2));
+    }
+}
+
+let args = vec!["--dem=dem.tif".to_string(), "--output=slope.tif".to_string()];
+Slope::new().run(args, "/path/to/data/", true, &ProgressBar).unwrap();
+```
+
+The reporter is also used to cancel a running tool, which then stops and returns an error of the
+`Interrupted` kind:
+
+```no_run
+use std::io::ErrorKind;
+use whitebox_common::utils::spawn;
+use whitebox_tools::tools::hydro_analysis::StochasticDepressionAnalysis;
+use whitebox_tools::tools::{CancellableProgress, CancellationToken, ConsoleProgress, WhiteboxTool};
+
+let token = CancellationToken::new();
+let cancel = token.clone();
+spawn(move || {
+    // e.g. when the user presses a Cancel button
+    cancel.cancel();
+});
+
+let args = vec!["--dem=dem.tif".to_string(), "--output=pdep.tif".to_string(), "--rmse=1.0".to_string(), "--range=100.0".to_string()];
+let reporter = CancellableProgress::new(&ConsoleProgress, token);
+match StochasticDepressionAnalysis::new().run(args, "/path/to/data/", false, &reporter) {
+    Err(e) if e.kind() == ErrorKind::Interrupted => println!("Cancelled"),
+    result => result.unwrap(),
+}
+```
+
+Tools that also provide an `execute` function operate on in-memory `Raster` objects and return
+their outputs without reading or writing any files. These rasters may be created in memory, e.g.
+with `Raster::initialize_using_config`, and an output may be saved by giving it a file name:
+
+```no_run
+use whitebox_raster::Raster;
+use whitebox_tools::tools::gis_analysis::{CostAllocation, CostDistance};
+
+let sources = Raster::new("sources.tif", "r").unwrap();
+let cost = Raster::new("cost.tif", "r").unwrap();
+let (_accumulated, backlink) = CostDistance::execute(&sources, &cost).unwrap();
+let mut allocation = CostAllocation::execute(&sources, &backlink).unwrap();
+allocation.set_file_name("allocation.tif");
+allocation.write().unwrap();
+```
+
+The tools with an `execute` function are `CostAllocation`, `CostDistance` and `CostPathway`.
+
+Rasters needn't be files. Rasters named with a URL scheme for which a `RasterProvider` is
+registered are read from, and written to, that provider, and so any tool can be run on them,
+e.g. on DEMs that an application holds in memory rather than in files. The built-in
+`MemoryRasterProvider` holds rasters in memory, and an application may fill it with the rasters
+that it decodes, or implement `RasterProvider` to use its own storage:
+
+```no_run
+use std::sync::Arc;
+use whitebox_raster::{register_raster_provider, MemoryRasterProvider, RasterProvider};
+use whitebox_tools::tools::terrain_analysis::Slope;
+use whitebox_tools::tools::{ConsoleProgress, WhiteboxTool};
+
+register_raster_provider("app", Arc::new(MemoryRasterProvider));
+# let (configs, elevations) = (Default::default(), vec![]);
+MemoryRasterProvider.write("app://dem.tif", &configs, &elevations).unwrap();
+let args = vec!["--dem=app://dem.tif".to_string(), "--output=app://slope.tif".to_string()];
+Slope::new().run(args, "", false, &ConsoleProgress).unwrap();
+let (_configs, _slope) = MemoryRasterProvider.read("app://slope.tif").unwrap();
+```
+
+Vectors and LiDAR point clouds are supplied in the same way, by a `VectorProvider` (e.g.
+`MemoryVectorProvider`) of the whitebox_vector crate, registered with
+`register_vector_provider`, and a `LidarProvider` (e.g. `MemoryLidarProvider`) of the
+whitebox_lidar crate, registered with `register_lidar_provider`.
+
+With providers, the library can be built for wasm32, which has no file system, e.g. to analyze
+DEMs in a web browser (`cargo check --workspace --target wasm32-unknown-unknown`). There is no
+threading in wasm32 builds, and so tools run their workers one after another on the calling
+thread, and neither memory-mapped rasters, ZSTD compression, nor object stores and other URLs
+are available. Tools that write intermediate files, or that use rayon, still need a file system
+or threads, respectively.
+*/
+
+pub mod tools;
+
+use nalgebra as na;
+
+#[macro_use]
+extern crate serde_derive;
@@ -37,19 +37,17 @@ by the WhiteboxTools library:
 // pub mod rendering;
 // pub mod spatial_ref_system;
 // pub mod structures;
-pub mod tools;
 // pub mod utils;
 // pub mod vector;
 
-use crate::tools::ToolManager;
-use nalgebra as na;
 // use rstar;
 use std::env;
-use std::io::Error;
+use std::io::{Error, ErrorKind};
+use std::panic;
 use std::path;
-
-#[macro_use]
-extern crate serde_derive;
+use std::process;
+use whitebox_common::errors::{error_json, ErrorCause};
+use whitebox_tools::tools::{JsonProgress, ToolManager, DEFAULT_SERVER_ADDRESS};
 
 // extern crate late_static;
 // use late_static::LateStatic;
@@ -66,11 +64,32 @@ extern crate serde_derive;
 /// >>./whitebox_tools --wd='/Users/johnlindsay/Documents/data/' --run=DevFromMeanElev --input='DEM clipped.dep' --output='DEV raster.dep' -v
 /// ```
 
+/// The program exits with a code that identifies the cause of a failure (see the errors module of
+/// whitebox_common), e.g. 5 for inputs with differing grids, so that scripts can branch on it.
 fn main() {
-    match run() {
-        Ok(()) => {}
-        Err(err) => panic!("{}", err),
+    let (cause, message) = match panic::catch_unwind(run) {
+        Ok(Ok(())) => return,
+        Ok(Err(err)) => {
+            eprintln!("Error: {}", err);
+            (ErrorCause::of(&err), err.to_string())
+        }
+        Err(payload) => {
+            // the message of the panic has already been printed
+            let message = payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            (ErrorCause::Other, message)
+        }
+    };
+    let json_progress = env::args().any(|a| {
+        a.to_lowercase().replace("--", "-").replace("\"", "") == "-progress=json"
+    });
+    if json_progress {
+        println!("{}", serde_json::json!({ "error": error_json(cause, &message) }));
     }
+    process::exit(cause.exit_code());
 }
 
 // // This is just used for testing new features.
@@ -95,6 +114,8 @@ fn run() -> Result<(), Error> {
     let mut tool_parameters = false;
     let mut toolbox = false;
     let mut list_tools = false;
+    let mut list_presets = false;
+    let mut show_settings = false;
     let mut keywords: Vec<String> = vec![];
     let mut view_code = false;
     let mut tool_args_vec: Vec<String> = vec![];
@@ -114,6 +135,27 @@ fn run() -> Result<(), Error> {
 
     let mut configs = whitebox_common::configs::get_configs()?;
     let mut configs_modified = false;
+    let mut out_dtype: Option<String> = None;
+    let mut out_scale: Option<f64> = None;
+    let mut out_offset: Option<f64> = None;
+    let mut compute: Option<String> = None;
+    let mut json_progress = false;
+    let mut json_events = false;
+    let mut no_overwrite: Option<bool> = None;
+    let mut cog: Option<bool> = None;
+    let mut geotiff_compression: Option<String> = None;
+    let mut geotiff_predictor: Option<bool> = None;
+    let mut run_manifest: Option<String> = None;
+    let mut report_json: Option<bool> = None;
+    let mut rerun_manifest: Option<String> = None;
+    let mut self_test: Option<String> = None;
+    let mut server: Option<String> = None;
+    let mut benchmark: Option<usize> = None;
+    let mut netcdf_band: Option<usize> = None;
+    let mut netcdf_time: Option<String> = None;
+    let mut zarr_version: Option<u8> = None;
+    let mut max_procs: Option<isize> = None;
+    let mut numa_pinning: Option<bool> = None;
 
     // if args.contains(&String::from("--compress_rasters")) {
     //     // unsafe {
@@ -169,6 +211,108 @@ fn run() -> Result<(), Error> {
                 configs.working_directory = working_dir.clone();
                 configs_modified = true;
             }
+        } else if flag_val == "-report_json" || flag_val.starts_with("-report_json=") {
+            // must precede the -r/--run flag, which it would otherwise match
+            let mut v = arg
+                .replace("--report_json", "")
+                .replace("-report_json", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            report_json = Some(v.to_lowercase().contains("t") || v.is_empty());
+        } else if flag_val.starts_with("-rerun_failed") {
+            // must precede the -r/--run flag, which it would otherwise match
+            let mut v = arg
+                .replace("--rerun_failed", "")
+                .replace("-rerun_failed", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            rerun_manifest = Some(v);
+        } else if flag_val.starts_with("-raster_cache_mb") {
+            // must precede the -r/--run flag, which it would otherwise match
+            let mut v = arg
+                .replace("--raster_cache_mb", "")
+                .replace("-raster_cache_mb", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.trim().parse::<usize>().expect(&format!("Error parsing {}", v));
+            if val != configs.raster_cache_mb { // update value
+                configs.raster_cache_mb = val;
+                configs_modified = true;
+            }
+        } else if flag_val.starts_with("-remote_cache_mb") {
+            // must precede the -r/--run flag, which it would otherwise match
+            let mut v = arg
+                .replace("--remote_cache_mb", "")
+                .replace("-remote_cache_mb", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.trim().parse::<usize>().expect(&format!("Error parsing {}", v));
+            if val != configs.remote_cache_mb { // update value
+                configs.remote_cache_mb = val;
+                configs_modified = true;
+            }
+        } else if flag_val.starts_with("-tile_cache_mb") {
+            let mut v = arg
+                .replace("--tile_cache_mb", "")
+                .replace("-tile_cache_mb", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.trim().parse::<usize>().expect(&format!("Error parsing {}", v));
+            if val != configs.tile_cache_mb { // update value
+                configs.tile_cache_mb = val;
+                configs_modified = true;
+            }
+        } else if flag_val.starts_with("-postgis_connection") {
+            // the connection string itself contains '=' signs and may quote values, e.g.
+            // host=localhost dbname=gis password='a b', so only the surrounding quotes are removed
+            let v = match arg.find('=') {
+                Some(i) => &arg[i + 1..],
+                None => "",
+            };
+            let val = v.trim().trim_matches(|c| c == '"').trim().to_string();
+            if val != configs.postgis_connection { // update value
+                configs.postgis_connection = val;
+                configs_modified = true;
+            }
+        } else if flag_val.starts_with("-presets_file") {
+            let mut v = arg
+                .replace("--presets_file", "")
+                .replace("-presets_file", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.trim().to_string();
+            if val != configs.presets_file { // update value
+                configs.presets_file = val;
+                configs_modified = true;
+            }
+        } else if flag_val.starts_with("-manifest") {
+            let mut v = arg
+                .replace("--manifest", "")
+                .replace("-manifest", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            run_manifest = Some(v.trim().to_string());
         } else if arg.starts_with("-run") || arg.starts_with("--run") || arg.starts_with("-r") {
             let mut v = arg
                 .replace("--run", "")
@@ -214,6 +358,21 @@ fn run() -> Result<(), Error> {
             }
             tool_name = v;
             toolbox = true;
+        } else if flag_val.starts_with("-listpresets") || flag_val.starts_with("-list_presets") {
+            let mut v = arg
+                .replace("--listpresets", "")
+                .replace("-listpresets", "")
+                .replace("--list_presets", "")
+                .replace("-list_presets", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            tool_name = v;
+            list_presets = true;
+        } else if flag_val == "-settings" {
+            show_settings = true;
         } else if arg.starts_with("-listtools")
             || arg.starts_with("--listtools")
             || arg.starts_with("-list_tools")
@@ -276,6 +435,74 @@ fn run() -> Result<(), Error> {
                     configs_modified = true;
                 }
             }
+        } else if arg.starts_with("-cache_statistics") || arg.starts_with("--cache_statistics") {
+            let mut v = arg
+                .replace("--cache_statistics", "")
+                .replace("-cache_statistics", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.to_lowercase().contains("t") || v.is_empty();
+            if val != configs.cache_statistics { // update value
+                configs.cache_statistics = val;
+                configs_modified = true;
+            }
+        } else if arg.starts_with("-provenance") || arg.starts_with("--provenance") {
+            let mut v = arg
+                .replace("--provenance", "")
+                .replace("-provenance", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.to_lowercase().contains("t") || v.is_empty();
+            if val != configs.provenance { // update value
+                configs.provenance = val;
+                configs_modified = true;
+            }
+        } else if flag_val.starts_with("-default_raster_format") {
+            let mut v = arg
+                .replace("--default_raster_format", "")
+                .replace("-default_raster_format", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.trim().trim_start_matches('.').to_lowercase();
+            if !whitebox_raster::is_writable_raster_extension(&val) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The --default_raster_format flag must be the extension of a raster format that can be written, e.g. 'tif' or 'dep', but {} is not.", val),
+                ));
+            }
+            if val != configs.default_raster_format { // update value
+                configs.default_raster_format = val;
+                configs_modified = true;
+            }
+        } else if flag_val.starts_with("-whitebox_raster_compression") {
+            let mut v = arg
+                .replace("--whitebox_raster_compression", "")
+                .replace("-whitebox_raster_compression", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.trim().to_lowercase();
+            if val != "v1" && val != "none" && val != "lz4" && val != "deflate" && val != "zstd" {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The --whitebox_raster_compression flag must be one of 'v1', 'none', 'lz4', 'deflate', or 'zstd'.",
+                ));
+            }
+            if val != configs.whitebox_raster_compression { // update value
+                configs.whitebox_raster_compression = val;
+                configs_modified = true;
+            }
         } else if arg.starts_with("-v") || arg.starts_with("--verbose") {
             let mut v = arg
                 .replace("-v", "")
@@ -306,11 +533,241 @@ fn run() -> Result<(), Error> {
             if v.starts_with("=") {
                 v = v[1..v.len()].to_string();
             }
-            let val = v.parse::<isize>().expect(&format!("Error parsing {}", v));
-            if val != configs.max_procs {
-                configs.max_procs = val;
-                configs_modified = true;
+            let val = v.trim().parse::<isize>().map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The --max_procs flag must be a whole number, e.g. 4, or -1 for all processors, not '{}'.", v),
+                )
+            })?;
+            if val == 0 || val < -1 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The --max_procs flag must be at least 1, or -1 for all available processors.",
+                ));
+            }
+            max_procs = Some(val);
+        } else if flag_val.starts_with("-numa_pinning") {
+            let mut v = arg
+                .replace("--numa_pinning", "")
+                .replace("-numa_pinning", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            numa_pinning = Some(v.to_lowercase().contains("t") || v.is_empty());
+        } else if ["-out_dtype", "-out_dtype_scale", "-out_dtype_offset"].contains(&flag_val.split('=').next().unwrap_or("")) {
+            // These are matched exactly, rather than by prefix, since tools have parameters with
+            // similar names, e.g. the --out_scale output file of MaxElevationDeviation.
+            let key = flag_val.split('=').next().unwrap_or("").to_string();
+            let v = arg
+                .split_once('=')
+                .map(|(_, v)| v)
+                .unwrap_or("")
+                .replace("\"", "")
+                .replace("\'", "");
+            if key == "-out_dtype" {
+                let dtype = v.trim().to_lowercase();
+                if dtype != "auto" && dtype != "native" {
+                    dtype.parse::<whitebox_raster::DataType>()?;
+                }
+                out_dtype = Some(dtype);
+            } else {
+                let value = v.trim().parse::<f64>().map_err(|_| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Error parsing -{}: '{}' is not a number.", key, v.trim()),
+                    )
+                })?;
+                if key == "-out_dtype_scale" {
+                    if value == 0.0 || !value.is_finite() {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "The --out_dtype_scale flag must be a non-zero number.",
+                        ));
+                    }
+                    out_scale = Some(value);
+                } else {
+                    out_offset = Some(value);
+                }
             }
+        } else if flag_val.starts_with("-compute") {
+            let mut v = arg
+                .replace("--compute", "")
+                .replace("-compute", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let device = v.trim().to_lowercase();
+            if device != "cpu" && device != "gpu" {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The --compute flag must be either 'cpu' or 'gpu'.",
+                ));
+            }
+            compute = Some(device);
+        } else if flag_val.starts_with("-progress") {
+            let mut v = arg
+                .replace("--progress", "")
+                .replace("-progress", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let format = v.trim().to_lowercase();
+            if format != "console" && format != "json" {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The --progress flag must be either 'console' or 'json'.",
+                ));
+            }
+            json_progress = format == "json";
+        } else if flag_val == "-json" {
+            json_events = true;
+        } else if flag_val.starts_with("-no_overwrite") {
+            let mut v = arg
+                .replace("--no_overwrite", "")
+                .replace("-no_overwrite", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            no_overwrite = Some(v.to_lowercase().contains("t") || v.is_empty());
+        } else if flag_val == "-cog" || flag_val.starts_with("-cog=") {
+            let mut v = arg
+                .replace("--cog", "")
+                .replace("-cog", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            cog = Some(v.to_lowercase().contains("t") || v.is_empty());
+        } else if flag_val.starts_with("-geotiff_compression") {
+            let mut v = arg
+                .replace("--geotiff_compression", "")
+                .replace("-geotiff_compression", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let val = v.trim().to_lowercase();
+            if val != "none" && val != "lzw" && val != "deflate" && val != "zstd" {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The --geotiff_compression flag must be one of 'none', 'lzw', 'deflate', or 'zstd'.",
+                ));
+            }
+            geotiff_compression = Some(val);
+        } else if flag_val.starts_with("-geotiff_predictor") {
+            let mut v = arg
+                .replace("--geotiff_predictor", "")
+                .replace("-geotiff_predictor", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            geotiff_predictor = Some(v.to_lowercase().contains("t") || v.is_empty());
+        } else if flag_val == "-band" || flag_val.starts_with("-band=") {
+            let mut v = arg
+                .replace("--band", "")
+                .replace("-band", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            netcdf_band = Some(match v.trim().parse::<usize>() {
+                Ok(b) if b > 0 => b,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The --band flag must be given a band number of 1 or more, e.g. --band=3",
+                    ))
+                }
+            });
+        } else if flag_val == "-time" || flag_val.starts_with("-time=") {
+            let mut v = arg
+                .replace("--time", "")
+                .replace("-time", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            netcdf_time = Some(v.trim().to_string());
+        } else if flag_val.starts_with("-zarr_version") {
+            let mut v = arg
+                .replace("--zarr_version", "")
+                .replace("-zarr_version", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            zarr_version = Some(match v.trim() {
+                "2" => 2,
+                "3" => 3,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The --zarr_version flag must be given a Zarr format version of 2 or 3, e.g. --zarr_version=3",
+                    ))
+                }
+            });
+        } else if flag_val.starts_with("-benchmark") {
+            let mut v = arg
+                .replace("--benchmark", "")
+                .replace("-benchmark", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            benchmark = Some(if v.trim().is_empty() {
+                3
+            } else {
+                v.trim().parse::<usize>().map_err(|_| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        "The --benchmark flag must be given a number of repetitions, e.g. --benchmark=5",
+                    )
+                })?
+            });
+        } else if flag_val == "-server" || flag_val.starts_with("-server=") {
+            let mut v = arg
+                .replace("--server", "")
+                .replace("-server", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            let address = v.trim();
+            // a port alone is served on the loopback interface
+            server = Some(if address.is_empty() {
+                DEFAULT_SERVER_ADDRESS.to_string()
+            } else if address.parse::<u16>().is_ok() {
+                format!("127.0.0.1:{}", address)
+            } else {
+                address.to_string()
+            });
+        } else if flag_val.starts_with("-selftest") {
+            let mut v = arg
+                .replace("--selftest", "")
+                .replace("-selftest", "")
+                .replace("\"", "")
+                .replace("\'", "");
+            if v.starts_with("=") {
+                v = v[1..v.len()].to_string();
+            }
+            self_test = Some(v);
         } else if arg.starts_with("-version") || arg.starts_with("--version") {
             version();
             return Ok(());
@@ -352,15 +809,122 @@ fn run() -> Result<(), Error> {
     //     configs.working_directory = working_dir.clone();
     // }
 
+    // The output data type, compute, no_overwrite, cog, GeoTIFF compression, manifest, report
+    // JSON, NetCDF band and time, Zarr version, max_procs, and NUMA pinning flags apply only to
+    // the current run when used with --run, or to the runs of a server when used with --server,
+    // and otherwise update the settings.json file.
+    if out_dtype.is_some()
+        || out_scale.is_some()
+        || out_offset.is_some()
+        || compute.is_some()
+        || no_overwrite.is_some()
+        || cog.is_some()
+        || geotiff_compression.is_some()
+        || geotiff_predictor.is_some()
+        || run_manifest.is_some()
+        || report_json.is_some()
+        || netcdf_band.is_some()
+        || netcdf_time.is_some()
+        || zarr_version.is_some()
+        || max_procs.is_some()
+        || numa_pinning.is_some()
+    {
+        let mut out_configs = configs.clone();
+        if let Some(v) = out_dtype {
+            out_configs.out_dtype = v;
+        }
+        if let Some(v) = out_scale {
+            out_configs.out_scale = v;
+        }
+        if let Some(v) = out_offset {
+            out_configs.out_offset = v;
+        }
+        if let Some(v) = compute {
+            out_configs.compute = v;
+        }
+        if let Some(v) = no_overwrite {
+            out_configs.no_overwrite = v;
+        }
+        if let Some(v) = cog {
+            out_configs.cog = v;
+        }
+        if let Some(v) = geotiff_compression {
+            out_configs.geotiff_compression = v;
+        }
+        if let Some(v) = geotiff_predictor {
+            out_configs.geotiff_predictor = v;
+        }
+        if let Some(v) = run_manifest {
+            out_configs.run_manifest = v;
+        }
+        if let Some(v) = report_json {
+            out_configs.report_json = v;
+        }
+        if let Some(v) = netcdf_band {
+            out_configs.netcdf_band = v;
+        }
+        if let Some(v) = netcdf_time {
+            out_configs.netcdf_time = v;
+        }
+        if let Some(v) = zarr_version {
+            out_configs.zarr_version = v;
+        }
+        if let Some(v) = max_procs {
+            out_configs.max_procs = v;
+        }
+        if let Some(v) = numa_pinning {
+            out_configs.numa_pinning = v;
+        }
+        if run_tool || rerun_manifest.is_some() || server.is_some() {
+            whitebox_common::configs::set_run_configs(&out_configs);
+        } else {
+            configs = out_configs;
+            configs_modified = true;
+        }
+    }
+
     if configs_modified {
         whitebox_common::configs::save_configs(&configs)?;
     }
 
-    let tm = ToolManager::new(&configs.working_directory, &configs.verbose_mode)?;
-    if run_tool {
+    if show_settings {
+        return print_settings();
+    }
+
+    if benchmark.is_some() && !run_tool {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The --benchmark flag must be used with --run to specify the tool to benchmark.",
+        ));
+    }
+
+    if json_events && !run_tool {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The --json flag must be used with --run to specify the tool to run.",
+        ));
+    }
+
+    let mut tm = ToolManager::new(&configs.working_directory, &configs.verbose_mode)?;
+    if json_progress {
+        tm.set_progress_reporter(Box::new(JsonProgress));
+    }
+    if let Some(address) = server {
+        return tm.serve(&address);
+    } else if let Some(filter) = self_test {
+        return tm.self_test(&filter);
+    } else if let Some(manifest) = rerun_manifest {
+        return tm.rerun_failed(&manifest);
+    } else if run_tool {
         if tool_name.is_empty() && keywords.len() > 0 {
             tool_name = keywords[0].clone();
         }
+        if let Some(repetitions) = benchmark {
+            return tm.benchmark(tool_name, tool_args_vec, repetitions);
+        }
+        if json_events {
+            return tm.run_tool_json(tool_name, tool_args_vec);
+        }
         return tm.run_tool(tool_name, tool_args_vec);
     } else if tool_help {
         if tool_name.is_empty() && keywords.len() > 0 {
@@ -380,6 +944,11 @@ fn run() -> Result<(), Error> {
             tool_name = String::new();
         }
         return tm.toolbox(tool_name);
+    } else if list_presets {
+        if tool_name.is_empty() && keywords.len() > 0 {
+            tool_name = keywords[0].clone();
+        }
+        return tm.list_presets(tool_name);
     } else if list_tools {
         if keywords.len() == 0 {
             tm.list_tools();
@@ -396,6 +965,25 @@ fn run() -> Result<(), Error> {
     Ok(())
 }
 
+/// Prints the settings in effect as JSON, along with the settings that the configuration file and
+/// environment variables override and the locations of the settings and configuration files.
+fn print_settings() -> Result<(), Error> {
+    let configs = whitebox_common::configs::get_configs()?;
+    let overrides: serde_json::Map<String, serde_json::Value> =
+        whitebox_common::configs::read_overrides()?
+            .into_iter()
+            .map(|o| (o.key, serde_json::Value::String(o.source)))
+            .collect();
+    let settings = serde_json::json!({
+        "settings": configs,
+        "overridden_by": overrides,
+        "settings_file": whitebox_common::configs::settings_file()?,
+        "config_file": whitebox_common::configs::config_file(),
+    });
+    println!("{}", serde_json::to_string_pretty(&settings)?);
+    Ok(())
+}
+
 fn help() {
     let mut ext = "";
     if cfg!(target_os = "windows") {
@@ -408,18 +996,56 @@ fn help() {
 
 The following commands are recognized:
 --cd, --wd          Changes the working directory; used in conjunction with --run flag.
+--band              Sets the band (1-based) read from multi-dimensional NetCDF and Zarr inputs, i.e. the position along their leading (e.g. time or depth) dimensions; applies to a single run when used with --run, e.g. --band=3
+--benchmark         Runs a tool repeatedly at increasing thread counts and prints a JSON performance report; used with --run, e.g. --benchmark=5
+--cache_statistics  Sets the cache_statistics option in the settings.json file; determines if the statistics of newly created rasters are cached in .aux.xml files. e.g. --cache_statistics=false
+--cog               Writes output GeoTIFFs as Cloud-Optimized GeoTIFFs (tiled, with internal overviews); applies to a single run when used with --run, e.g. --cog
+--compute           Sets the device used by tools that support GPU acceleration (cpu, gpu); applies to a single run when used with --run, e.g. --compute=gpu
 --compress_rasters  Sets the compress_raster option in the settings.json file; determines if newly created rasters are compressed. e.g. --compress_rasters=true
+--default_raster_format  Sets the default_raster_format option in the settings.json file; the extension, and so the format, of output rasters whose file names have none. e.g. --default_raster_format=dep
+--geotiff_compression  Sets the compression of the tiles of output GeoTIFFs (none, lzw, deflate, zstd), used when compress_rasters is true; applies to a single run when used with --run, e.g. --geotiff_compression=zstd
+--geotiff_predictor  Applies a predictor (horizontal differencing, or the floating-point predictor for float data) ahead of GeoTIFF compression; applies to a single run when used with --run, e.g. --geotiff_predictor
 -h, --help          Prints help information.
+--json              Reports a tool run as JSON events (start, metadata, progress, messages, and its end, with the elapsed time and outputs), one per line, in place of text; used with --run, e.g. --json
 -l, --license       Prints the whitebox-tools license. Tool names may also be used, --license=\"Slope\"
+--listpresets       Prints the parameter presets of a tool, or of all tools, as JSON, along with the location of the user's presets file; --listpresets=\"LidarTINGridding\".
 --listtools         Lists all available tools. Keywords may also be used, --listtools slope.
---max_procs         Sets the maximum number of processors used. -1 = all available processors. e.g. --max_procs=2
+--manifest          Appends a record of each tool run (parameters, duration, exit status, outputs) to a JSON Lines file; applies to a single run when used with --run, e.g. --manifest=runs.jsonl
+--max_procs         Sets the maximum number of processors used. -1 = all available processors; applies to a single run when used with --run, e.g. --max_procs=2
+--no_overwrite      Prevents tools from replacing existing output files; applies to a single run when used with --run, e.g. --no_overwrite
+--numa_pinning      Pins the threads of the large-memory hydrological tools to the processors of NUMA nodes (Linux only); applies to a single run when used with --run, e.g. --numa_pinning
+--out_dtype         Sets the data type of output rasters (auto, native, u8, i16, u16, i32, f32, f64); applies to a single run when used with --run, e.g. --out_dtype=i16
+--out_dtype_offset  Sets the offset subtracted from output raster values when --out_dtype is used, e.g. --out_dtype_offset=-1000
+--out_dtype_scale   Sets the scale factor that output raster values are divided by when --out_dtype is used, e.g. --out_dtype_scale=0.01
+--postgis_connection  Sets the postgis_connection option in the settings.json file; the connection string of the database holding vector datasets named postgis:schema.table, which requires a build with the postgis feature. e.g. --postgis_connection=\"host=localhost dbname=gis user=postgres\"
+--preset            Applies a named set of parameter values, stored in the user's or the shared presets file, to a tool run; values given in the run take precedence, and a tool's preset named 'default' is applied unless --preset=none. Used with --run, e.g. --preset=lidar_dem_1m
+--presets_file      Sets the presets_file option in the settings.json file; a shared JSON file of tool parameter presets, e.g. one maintained for a team, read along with the user's presets file. e.g. --presets_file=\"/shared/wbt_presets.json\"
+--progress          Sets the format in which tools report their progress, either console (e.g. 'Progress: 45%') or json (e.g. {\"label\":\"Progress\",\"progress\":45}), e.g. --progress=json
+--provenance        Sets the provenance option in the settings.json file; determines if a .provenance.json sidecar recording the tool, parameters, and input file hashes is written for each output. e.g. --provenance=false
+--raster_cache_mb   Sets the raster_cache_mb option in the settings.json file; the size of the in-memory cache of recently read rasters, which speeds up repeated tool runs on the same inputs within one process (0 disables it). e.g. --raster_cache_mb=2048
+--remote_cache_mb   Sets the remote_cache_mb option in the settings.json file; the size of the disk cache of the blocks of GeoTIFFs read from URLs (e.g. https://.../dem.tif) and object stores, which spares repeated transfers when tools are run on the same remote input (0 disables it). e.g. --remote_cache_mb=4096
+--rerun_failed      Re-runs each tool run recorded in a manifest file whose most recent attempt failed, e.g. --rerun_failed=runs.jsonl
+--report_json       Also writes the data underlying the HTML reports of tools (tables and chart data) to a .json file alongside each report; applies to a single run when used with --run, e.g. --report_json
 -r, --run           Runs a tool; used in conjunction with --wd flag; -r=\"LidarInfo\".
+--settings          Prints the settings in effect as JSON, along with the settings overridden by the configuration file (named by WBT_CONFIG_FILE, or config.json in the user's WhiteboxTools configuration directory) and by WBT_* environment variables, e.g. WBT_MAX_PROCS=4.
+--selftest          Runs the built-in tests of tools on synthetic datasets; a tool or test name may be used to select tests, e.g. --selftest=Slope
+--server            Runs as a server that keeps running and accepts JSON-RPC 2.0 tool invocations (run_tool, cancel, list_tools, tool_parameters, clear_cache, shutdown), one per line, over TCP connections, streams progress as notifications, and keeps recently read rasters in memory between runs. An address or port may be given; by default, a free port on the loopback interface is used and reported on the first line of output, e.g. --server=127.0.0.1:9300
+--time              Selects the time step read from NetCDF inputs by date or time coordinate value, with --band then selecting among any other leading dimensions; applies to a single run when used with --run, e.g. --time=2020-07-01
+--tile_cache_mb     Sets the tile_cache_mb option in the settings.json file; the size of the tile cache of each tile-backed raster. When non-zero, supporting tools stream Whitebox raster inputs too large for the cache rather than reading them into memory (0 disables out-of-core processing). e.g. --tile_cache_mb=4096
 --toolbox           Prints the toolbox associated with a tool; --toolbox=Slope.
 --toolhelp          Prints the help associated with a tool; --toolhelp=\"LidarInfo\".
 --toolparameters    Prints the parameters (in json form) for a specific tool; --toolparameters=\"LidarInfo\".
 -v                  Verbose mode. Without this flag, tool outputs will not be printed.
 --viewcode          Opens the source code of a tool in a web browser; --viewcode=\"LidarInfo\".
 --version           Prints the version information.
+--whitebox_raster_compression  Sets the whitebox_raster_compression option in the settings.json file; determines the layout of newly created Whitebox (.dep) rasters, either v1 or the tiled v2 format with none, lz4, deflate, or zstd compression. e.g. --whitebox_raster_compression=lz4
+--zarr_version      Sets the Zarr format version (2 or 3) of output Zarr (.zarr) stores; applies to a single run when used with --run, e.g. --zarr_version=3
+
+Exit Codes:
+0 success; 1 other failure; 2 invalid parameter; 3 file not found; 4 unsupported format;
+5 grid mismatch (inputs with differing rows, columns, or extents); 6 out of memory; 7 cancelled;
+8 license (an extension tool without a valid license).
+With --json or --progress=json, a failure is also reported as JSON, with its kind, e.g. grid_mismatch.
 
 Example Usage:
 >> .*EXE_NAME -r=lidar_info --cd=\"*path*to*data*\" -i=input.las --vlr --geokeys
@@ -0,0 +1,175 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Benchmark mode runs a tool repeatedly on the user's own inputs and prints a JSON report
+of its performance, so that timings can be compared across machines and releases. The tool is
+run `repetitions` times at each of a series of thread counts (1, 2, 4, ..., up to the number of
+available processors, or max_procs if it is set), after one untimed warm-up run that brings
+the inputs into the operating system's file cache. For each run, the time spent reading and
+writing datasets is recorded separately from the total wall time (see `time_io`), and the
+remainder is reported as compute time. Peak resident memory is only reported on Linux, where
+it is read from /proc and reset between runs.
+*/
+
+use super::{ConsoleProgress, ToolManager};
+use std::io::{Error, ErrorKind};
+use std::time::Instant;
+use whitebox_common::configs::{get_configs, set_run_configs};
+use whitebox_common::errors::ErrorCause;
+use whitebox_common::utils::{io_time, reset_io_time, ThreadPool};
+
+const VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSION");
+
+#[derive(Serialize, Debug)]
+struct BenchmarkReport {
+    tool: String,
+    version: String,
+    args: Vec<String>,
+    working_directory: String,
+    os: String,
+    arch: String,
+    available_threads: usize,
+    repetitions: usize,
+    results: Vec<ThreadCountResult>,
+}
+
+#[derive(Serialize, Debug)]
+struct ThreadCountResult {
+    threads: usize,
+    wall_seconds: Vec<f64>,
+    wall_seconds_min: f64,
+    wall_seconds_median: f64,
+    wall_seconds_mean: f64,
+    io_seconds_mean: f64,
+    compute_seconds_mean: f64,
+    /// The median wall time with one thread divided by the median wall time with this many.
+    speedup: f64,
+    peak_rss_bytes: Option<u64>,
+}
+
+/// Runs the benchmark and prints its report to standard output.
+pub(crate) fn run_benchmark(
+    tm: &ToolManager,
+    tool_name: &str,
+    args: &[String],
+    repetitions: usize,
+) -> Result<(), Error> {
+    if repetitions == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The number of benchmark repetitions must be at least 1.",
+        ));
+    }
+    let tool = tm.get_tool(tool_name).ok_or_else(|| {
+        ErrorCause::InvalidParameter.error(format!("Unrecognized tool name {}.", tool_name))
+    })?;
+    let configs = get_configs()?;
+    let available_threads = ThreadPool::new(&configs).num_threads();
+    let mut thread_counts = vec![];
+    let mut n = 1;
+    while n < available_threads {
+        thread_counts.push(n);
+        n *= 2;
+    }
+    thread_counts.push(available_threads);
+
+    // warm-up run
+    tool.run(args.to_vec(), &tm.working_dir, false, &ConsoleProgress)?;
+
+    let mut results: Vec<ThreadCountResult> = vec![];
+    for &threads in &thread_counts {
+        let mut run_configs = configs.clone();
+        run_configs.max_procs = threads as isize;
+        set_run_configs(&run_configs);
+
+        let mut wall_seconds = vec![];
+        let mut io_seconds = vec![];
+        let mut peak_rss: Option<u64> = None;
+        for _ in 0..repetitions {
+            reset_peak_rss();
+            reset_io_time();
+            let start = Instant::now();
+            tool.run(args.to_vec(), &tm.working_dir, false, &ConsoleProgress)?;
+            wall_seconds.push(start.elapsed().as_secs_f64());
+            io_seconds.push(io_time().as_secs_f64());
+            if let Some(rss) = peak_rss_bytes() {
+                peak_rss = Some(peak_rss.map_or(rss, |p: u64| p.max(rss)));
+            }
+        }
+        let wall_seconds_mean = mean(&wall_seconds);
+        let io_seconds_mean = mean(&io_seconds);
+        let wall_seconds_median = median(&wall_seconds);
+        let speedup = match results.first() {
+            Some(r) => r.wall_seconds_median / wall_seconds_median,
+            None => 1.0,
+        };
+        results.push(ThreadCountResult {
+            threads,
+            wall_seconds_min: wall_seconds.iter().cloned().fold(f64::INFINITY, f64::min),
+            wall_seconds_median,
+            wall_seconds_mean,
+            io_seconds_mean,
+            compute_seconds_mean: (wall_seconds_mean - io_seconds_mean).max(0.0),
+            speedup,
+            peak_rss_bytes: peak_rss,
+            wall_seconds,
+        });
+    }
+    set_run_configs(&configs);
+
+    let report = BenchmarkReport {
+        tool: tool.get_tool_name(),
+        version: VERSION.unwrap_or("unknown").to_string(),
+        args: args.to_vec(),
+        working_directory: tm.working_dir.clone(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        available_threads,
+        repetitions,
+        results,
+    };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Returns the peak resident set size of the process since it was last reset.
+#[cfg(target_os = "linux")]
+fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kb = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_rss_bytes() -> Option<u64> {
+    None
+}
+
+/// Resets the peak resident set size to the current resident set size.
+#[cfg(target_os = "linux")]
+fn reset_peak_rss() {
+    let _ = std::fs::write("/proc/self/clear_refs", "5");
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reset_peak_rss() {}
@@ -112,33 +112,10 @@ impl WhiteboxTool for AddPointCoordinatesToTable {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
 
         let mut progress: usize;
         let mut old_progress: usize = 1;
@@ -154,11 +131,7 @@ impl WhiteboxTool for AddPointCoordinatesToTable {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
         if verbose {
             println!("Reading data...")
@@ -206,11 +179,12 @@ impl WhiteboxTool for AddPointCoordinatesToTable {
             atts.push(FieldData::Real(record.points[0].y));
             output.attributes.add_record(atts, false);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
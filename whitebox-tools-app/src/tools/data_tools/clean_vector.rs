@@ -10,7 +10,7 @@ use crate::tools::*;
 use whitebox_common::structures::Point2D;
 use whitebox_vector::*;
 use std::env;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to remove all features in Shapefiles that are of the `null` ShapeType. It also
@@ -122,43 +122,14 @@ impl WhiteboxTool for CleanVector {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -175,13 +146,9 @@ impl WhiteboxTool for CleanVector {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         // read the input file
         let input = Shapefile::read(&input_file)?;
@@ -252,11 +219,12 @@ impl WhiteboxTool for CleanVector {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,477 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use crate::tools::*;
+use serde_json::{json, Value};
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool compares two rasters (`--input1` and `--input2`) and reports whether they are identical,
+/// within a tolerance (`--tolerance`), as JSON. It is intended for validating the outputs of a workflow
+/// after migrating it between versions of WhiteboxTools or between raster formats. The report lists
+/// the differences between the headers of the rasters (their dimensions, extents, resolutions, data
+/// types, nodata values, z units, and coordinate reference systems) and summarizes the differences
+/// between their cell values, i.e. the numbers of cells compared and of those that differ by more than
+/// the tolerance, the number of cells that are nodata in one raster but not the other, the largest
+/// absolute difference and its location, the mean difference (input2 - input1), and the root mean
+/// square error (RMSE). Cells that are nodata in both rasters are equal. The rasters are reported as
+/// `identical` when their cell values match within the tolerance and their headers don't differ;
+/// `values_match` reports the former alone, since a change of format may legitimately change the data
+/// type or nodata value of a raster. Cell values can only be compared when the rasters have the same
+/// numbers of rows and columns.
+///
+/// The report is printed to the standard output or, if an output file (`--output`) is specified,
+/// written to a JSON file. The spatial distribution of the differences (input2 - input1) may also be
+/// written to a raster (`--diff`), in which cells that are nodata in either input are nodata.
+///
+/// # See Also
+/// `CompareVectors`, `RasterSummary`, `RootMeanSquareError`, `Subtract`
+pub struct CompareRasters {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl CompareRasters {
+    pub fn new() -> CompareRasters {
+        // public constructor
+        let name = "CompareRasters".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Reports whether two rasters are identical within a tolerance, with their header and cell differences, as JSON."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Raster File 1".to_owned(),
+            flags: vec!["--input1".to_owned()],
+            description: "First input raster file, e.g. the reference.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Raster File 2".to_owned(),
+            flags: vec!["--input2".to_owned()],
+            description: "Second input raster file, which is compared with the first.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output JSON File (optional)".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output JSON file; if unspecified, the report is printed.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Difference Raster (optional)".to_owned(),
+            flags: vec!["--diff".to_owned()],
+            description: "Optional output raster of the differences between the cell values (input2 - input1).".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tolerance".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Largest absolute difference between cell values that are considered equal.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input1=slope_v2_0.tif --input2=slope_v2_1.tif -o=comparison.json --diff=slope_diff.tif --tolerance=0.001", short_exe, name).replace("*", &sep);
+
+        CompareRasters {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for CompareRasters {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input1_file = parsed.get_string("input1").unwrap_or_default();
+        let input2_file = parsed.get_string("input2").unwrap_or_default();
+        let output_file = parsed
+            .get_string("output")
+            .filter(|f| !f.trim().is_empty())
+            .map(|f| resolve_path(f.trim(), working_directory));
+        let diff_file = parsed
+            .get_string("diff")
+            .filter(|f| !f.trim().is_empty())
+            .map(|f| resolve_path(f.trim(), working_directory));
+        let tolerance = parsed.get_f64("tolerance").unwrap_or(0.0);
+
+        // The banner is only printed when the report isn't, so that printed reports can be parsed.
+        let print_messages = verbose && output_file.is_some();
+        if print_messages {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if !(tolerance >= 0.0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --tolerance parameter must not be negative.",
+            ));
+        }
+
+        if print_messages {
+            println!("Reading data...")
+        };
+        let input1_file = resolve_input_path(&input1_file, working_directory)?;
+        let input2_file = resolve_input_path(&input2_file, working_directory)?;
+        let input1 = Arc::new(Raster::new(&input1_file, "r")?);
+        let input2 = Arc::new(Raster::new(&input2_file, "r")?);
+
+        let start = Instant::now();
+
+        let header_differences = header_differences(&input1.configs, &input2.configs);
+        let same_dimensions =
+            input1.configs.rows == input2.configs.rows && input1.configs.columns == input2.configs.columns;
+        if diff_file.is_some() && !same_dimensions {
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error(
+                "The difference raster can't be created because the input rasters have different numbers of rows or columns.",
+            ));
+        }
+
+        let mut cells = Value::Null;
+        let mut values_match = false;
+        let mut diff_raster = None;
+        if same_dimensions {
+            let rows = input1.configs.rows as isize;
+            let columns = input1.configs.columns as isize;
+            let out_nodata = -32768f64;
+            let num_procs = whitebox_common::utils::ThreadPool::from_settings().max_threads(rows as usize).num_threads() as isize;
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let input1 = input1.clone();
+                let input2 = input2.clone();
+                let tx = tx.clone();
+                spawn(move || {
+                    let (nodata1, nodata2) = (input1.configs.nodata, input2.configs.nodata);
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        let mut stats = DifferenceStats::default();
+                        let mut data = vec![out_nodata; columns as usize];
+                        for col in 0..columns {
+                            let z1 = input1.get_value(row, col);
+                            let z2 = input2.get_value(row, col);
+                            let is_nodata1 = is_nodata(z1, nodata1) || z1.is_nan();
+                            let is_nodata2 = is_nodata(z2, nodata2) || z2.is_nan();
+                            if is_nodata1 != is_nodata2 {
+                                stats.nodata_mismatches += 1;
+                            } else if !is_nodata1 {
+                                let diff = z2 - z1;
+                                stats.add(diff, tolerance, row, col);
+                                data[col as usize] = diff;
+                            }
+                        }
+                        if tx.send((row, data, stats)).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+
+            if let Some(diff_file) = &diff_file {
+                let mut configs = input1.configs.clone();
+                configs.nodata = out_nodata;
+                configs.data_type = if input1.configs.data_type == DataType::F64 || input2.configs.data_type == DataType::F64 {
+                    DataType::F64
+                } else {
+                    DataType::F32
+                };
+                configs.photometric_interp = PhotometricInterpretation::Continuous;
+                configs.palette = "blue_white_red.plt".to_string();
+                configs.metadata = vec![];
+                diff_raster = Some(Raster::initialize_using_config(diff_file, &configs));
+            }
+
+            let mut stats = DifferenceStats::default();
+            let mut progress: usize;
+            let mut old_progress: usize = 1;
+            for r in 0..rows {
+                let (row, data, row_stats) = rx.recv().expect("Error receiving data from thread.");
+                stats.merge(&row_stats);
+                if let Some(output) = diff_raster.as_mut() {
+                    output.set_row_data(row, data);
+                }
+                reporter.check_cancelled()?;
+                if print_messages {
+                    progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Comparing cells", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            values_match = stats.num_differing == 0 && stats.nodata_mismatches == 0;
+            cells = stats.to_json(&input1.configs);
+        }
+
+        let report = json!({
+            "identical": values_match && header_differences.is_empty(),
+            "values_match": values_match,
+            "input1": input1_file,
+            "input2": input2_file,
+            "tolerance": tolerance,
+            "header_differences": header_differences,
+            "cells": cells,
+        });
+        let json_str = match serde_json::to_string_pretty(&report) {
+            Ok(s) => s,
+            Err(e) => return Err(Error::new(ErrorKind::Other, format!("{}", e))),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if let (Some(mut output), Some(diff_file)) = (diff_raster, &diff_file) {
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Input 1: {}", input1_file));
+            output.add_metadata_entry(format!("Input 2: {}", input2_file));
+            output.add_metadata_entry("Difference: input2 - input1".to_string());
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+            if print_messages {
+                println!("Saving data...")
+            };
+            output.write()?;
+            if print_messages {
+                println!("Difference raster written to {}", diff_file);
+            }
+        }
+
+        match output_file {
+            Some(output_file) => {
+                let mut f = File::create(&output_file)?;
+                f.write_all(json_str.as_bytes())?;
+                if verbose {
+                    if values_match && header_differences.is_empty() {
+                        println!("The rasters are identical.");
+                    } else {
+                        println!("The rasters differ; see {} for details.", output_file);
+                    }
+                    println!(
+                        "{}",
+                        &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+                    );
+                }
+            }
+            None => println!("{}", json_str),
+        }
+
+        Ok(())
+    }
+}
+
+/// Summarizes the differences between the values of the cells that are valid in both rasters.
+#[derive(Default)]
+struct DifferenceStats {
+    num_compared: usize,
+    num_differing: usize,
+    nodata_mismatches: usize,
+    sum: f64,
+    sum_of_squares: f64,
+    max_abs_difference: f64,
+    max_location: Option<(isize, isize)>,
+}
+
+impl DifferenceStats {
+    fn add(&mut self, diff: f64, tolerance: f64, row: isize, col: isize) {
+        self.num_compared += 1;
+        // a NaN difference, e.g. of infinite values, isn't within any tolerance
+        if !(diff.abs() <= tolerance) {
+            self.num_differing += 1;
+        }
+        self.sum += diff;
+        self.sum_of_squares += diff * diff;
+        if diff.abs() > self.max_abs_difference || self.max_location.is_none() {
+            self.max_abs_difference = diff.abs();
+            self.max_location = Some((row, col));
+        }
+    }
+
+    fn merge(&mut self, other: &DifferenceStats) {
+        self.num_compared += other.num_compared;
+        self.num_differing += other.num_differing;
+        self.nodata_mismatches += other.nodata_mismatches;
+        self.sum += other.sum;
+        self.sum_of_squares += other.sum_of_squares;
+        if let Some(location) = other.max_location {
+            // ties go to the first cell in row-major order, so the location doesn't depend on
+            // the order in which rows are received
+            let better = match self.max_location {
+                None => true,
+                Some(current) => {
+                    other.max_abs_difference > self.max_abs_difference
+                        || (other.max_abs_difference == self.max_abs_difference && location < current)
+                }
+            };
+            if better {
+                self.max_abs_difference = other.max_abs_difference;
+                self.max_location = Some(location);
+            }
+        }
+    }
+
+    fn to_json(&self, configs: &RasterConfigs) -> Value {
+        // JSON has no representation of NaN or infinite values
+        let num = |v: f64| if v.is_finite() { json!(v) } else { Value::Null };
+        let n = self.num_compared as f64;
+        let (mean, rmse) = if self.num_compared > 0 {
+            (num(self.sum / n), num((self.sum_of_squares / n).sqrt()))
+        } else {
+            (Value::Null, Value::Null)
+        };
+        let max_location = match self.max_location {
+            Some((row, col)) => json!({
+                "row": row,
+                "column": col,
+                "x": configs.west + (col as f64 + 0.5) * configs.resolution_x,
+                "y": configs.north - (row as f64 + 0.5) * configs.resolution_y,
+            }),
+            None => Value::Null,
+        };
+        json!({
+            "num_compared": self.num_compared,
+            "num_differing": self.num_differing,
+            "num_nodata_mismatches": self.nodata_mismatches,
+            "max_abs_difference": if self.max_location.is_some() { num(self.max_abs_difference) } else { Value::Null },
+            "max_abs_difference_location": max_location,
+            "mean_difference": mean,
+            "rmse": rmse,
+        })
+    }
+}
+
+/// Lists the header properties in which two rasters differ, with the value of each.
+fn header_differences(c1: &RasterConfigs, c2: &RasterConfigs) -> Vec<Value> {
+    let mut differences = vec![];
+    let mut compare = |property: &str, v1: Value, v2: Value| {
+        if v1 != v2 {
+            differences.push(json!({ "property": property, "input1": v1, "input2": v2 }));
+        }
+    };
+    compare("rows", json!(c1.rows), json!(c2.rows));
+    compare("columns", json!(c1.columns), json!(c2.columns));
+    compare("bands", json!(c1.bands), json!(c2.bands));
+    // Coordinates are compared to a small fraction of a cell, since formats that store them as
+    // text, or as single-precision values, may round them.
+    let eps = 1e-6 * c1.resolution_x.abs().min(c1.resolution_y.abs());
+    for (property, v1, v2) in [
+        ("north", c1.north, c2.north),
+        ("south", c1.south, c2.south),
+        ("east", c1.east, c2.east),
+        ("west", c1.west, c2.west),
+        ("resolution_x", c1.resolution_x, c2.resolution_x),
+        ("resolution_y", c1.resolution_y, c2.resolution_y),
+    ] {
+        if !((v1 - v2).abs() <= eps) {
+            compare(property, json!(v1), json!(v2));
+        }
+    }
+    compare(
+        "data_type",
+        json!(format!("{:?}", c1.data_type)),
+        json!(format!("{:?}", c2.data_type)),
+    );
+    let nodata = |v: f64| if v.is_finite() { json!(v) } else { json!(v.to_string()) };
+    if !(c1.nodata == c2.nodata || (c1.nodata.is_nan() && c2.nodata.is_nan())) {
+        compare("nodata", nodata(c1.nodata), nodata(c2.nodata));
+    }
+    compare("z_units", json!(c1.z_units), json!(c2.z_units));
+    let (crs1, crs2) = (c1.crs(), c2.crs());
+    match crs1.matches(&crs2) {
+        Some(true) => {}
+        // a CRS that is known for only one raster is reported as a difference too
+        None if !crs1.is_known() && !crs2.is_known() => {}
+        _ => compare("crs", json!(crs1.name()), json!(crs2.name())),
+    }
+    differences
+}
@@ -0,0 +1,577 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::hausdorff_distance;
+use whitebox_common::spatial_ref_system::CoordinateReferenceSystem;
+use whitebox_common::structures::Point2D;
+use crate::tools::*;
+use whitebox_vector::*;
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// The greatest number of differing features that are listed individually in the report.
+const MAX_LISTED_FEATURES: usize = 1000;
+
+/// This tool compares two vector layers (`--input1` and `--input2`) and reports whether they are
+/// equivalent, as JSON. It is intended for regression testing, e.g. for validating that a refactored
+/// vector tool still produces the same outputs. The features of the two layers are matched either by
+/// the values of an ID field (`--id_field`) that both layers contain or, if no ID field is given, by
+/// proximity, in which case the features whose centres (the means of their vertices) are nearest one
+/// another are matched, provided that they are within the search distance (`--search_distance`), if one
+/// is given.
+///
+/// For each pair of matched features, the tool measures the deviation between their geometries as the
+/// Hausdorff distance, i.e. the greatest distance from a point of either geometry to the nearest point
+/// of the other, which is measured from the vertices of each geometry to the segments of the other.
+/// Geometries differ if their Hausdorff distance exceeds the geometry tolerance (`--tolerance`, in map
+/// units). The values of the attribute fields that both layers contain are also compared; numeric
+/// values differ if they differ by more than the attribute tolerance (`--attribute_tolerance`), and
+/// other values differ if they aren't equal.
+///
+/// The report includes the differences between the layers' shape types, numbers of features, fields,
+/// and coordinate reference systems; the numbers of matched and unmatched features; the greatest and
+/// mean Hausdorff distances; the numbers of differing values in each field; and, for up to 1000
+/// features, the record numbers (starting from 1) of the differing and unmatched features, along with
+/// their Hausdorff distances and differing values. The layers are reported as `identical` when all of
+/// their features are matched and none of them differ, and their headers don't differ. The report is
+/// printed to the standard output or, if an output file (`--output`) is specified, written to a JSON
+/// file.
+///
+/// # See Also
+/// `CompareRasters`, `CleanVector`
+pub struct CompareVectors {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl CompareVectors {
+    pub fn new() -> CompareVectors {
+        // public constructor
+        let name = "CompareVectors".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Matches the features of two vector layers and reports their geometry (Hausdorff) and attribute differences as JSON."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File 1".to_owned(),
+            flags: vec!["--input1".to_owned()],
+            description: "First input vector file, e.g. the reference.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Vector File 2".to_owned(),
+            flags: vec!["--input2".to_owned()],
+            description: "Second input vector file, which is compared with the first.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output JSON File (optional)".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output JSON file; if unspecified, the report is printed.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "ID Field (optional)".to_owned(),
+            flags: vec!["--id_field".to_owned()],
+            description: "Field by which features are matched; if unspecified, features are matched by proximity.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--input1".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Geometry Tolerance".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Largest Hausdorff distance, in map units, between geometries that are considered equal.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Attribute Tolerance".to_owned(),
+            flags: vec!["--attribute_tolerance".to_owned()],
+            description: "Largest absolute difference between numeric attribute values that are considered equal.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Search Distance (optional)".to_owned(),
+            flags: vec!["--search_distance".to_owned()],
+            description: "Greatest distance between the centres of features that are matched by proximity.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input1=streams_v2_0.shp --input2=streams_v2_1.shp -o=comparison.json --id_field=FID --tolerance=0.01
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --input1=peaks_old.shp --input2=peaks_new.shp --search_distance=5.0 --attribute_tolerance=0.001", short_exe, name).replace("*", &sep);
+
+        CompareVectors {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for CompareVectors {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input1_file = parsed.get_string("input1").unwrap_or_default();
+        let input2_file = parsed.get_string("input2").unwrap_or_default();
+        let output_file = parsed
+            .get_string("output")
+            .filter(|f| !f.trim().is_empty())
+            .map(|f| resolve_path(f.trim(), working_directory));
+        let id_field = parsed.get_string("id_field").unwrap_or_default().trim().to_string();
+        let tolerance = parsed.get_f64("tolerance").unwrap_or(0.0);
+        let attribute_tolerance = parsed.get_f64("attribute_tolerance").unwrap_or(0.0);
+        let search_distance = parsed.get_f64("search_distance").unwrap_or(f64::INFINITY);
+
+        // The banner is only printed when the report isn't, so that printed reports can be parsed.
+        let print_messages = verbose && output_file.is_some();
+        if print_messages {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if !(tolerance >= 0.0) || !(attribute_tolerance >= 0.0) || !(search_distance > 0.0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --tolerance and --attribute_tolerance parameters must not be negative, and the --search_distance must be greater than zero.",
+            ));
+        }
+
+        if print_messages {
+            println!("Reading data...")
+        };
+        let input1_file = resolve_input_path(&input1_file, working_directory)?;
+        let input2_file = resolve_input_path(&input2_file, working_directory)?;
+        let input1 = Shapefile::read(&input1_file)?;
+        let input2 = Shapefile::read(&input2_file)?;
+
+        let start = Instant::now();
+
+        if !id_field.is_empty()
+            && (input1.attributes.get_field_num(&id_field).is_none() || input2.attributes.get_field_num(&id_field).is_none())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The ID field '{}' must be contained in both input files.", id_field),
+            ));
+        }
+
+        // header differences
+        let mut header_differences = vec![];
+        let mut compare = |property: &str, v1: Value, v2: Value| {
+            if v1 != v2 {
+                header_differences.push(json!({ "property": property, "input1": v1, "input2": v2 }));
+            }
+        };
+        compare(
+            "shape_type",
+            json!(format!("{:?}", input1.header.shape_type)),
+            json!(format!("{:?}", input2.header.shape_type)),
+        );
+        compare("num_features", json!(input1.num_records), json!(input2.num_records));
+        let (crs1, crs2) = (
+            CoordinateReferenceSystem::from_wkt(&input1.projection),
+            CoordinateReferenceSystem::from_wkt(&input2.projection),
+        );
+        match crs1.matches(&crs2) {
+            Some(true) => {}
+            // a CRS that is known for only one layer is reported as a difference too
+            None if !crs1.is_known() && !crs2.is_known() => {}
+            _ => compare("crs", json!(crs1.name()), json!(crs2.name())),
+        }
+        let fields1 = input1.attributes.get_fields();
+        let fields2 = input2.attributes.get_fields();
+        let mut common_fields = vec![];
+        for f1 in fields1 {
+            match fields2.iter().find(|f2| f2.name == f1.name) {
+                Some(f2) => {
+                    if f2.field_type != f1.field_type {
+                        compare(
+                            &format!("field_type:{}", f1.name),
+                            json!(f1.field_type.to_string()),
+                            json!(f2.field_type.to_string()),
+                        );
+                    }
+                    common_fields.push(f1.name.clone());
+                }
+                None => compare(&format!("field:{}", f1.name), json!(true), json!(false)),
+            }
+        }
+        for f2 in fields2 {
+            if !fields1.iter().any(|f1| f1.name == f2.name) {
+                compare(&format!("field:{}", f2.name), json!(false), json!(true));
+            }
+        }
+
+        if print_messages {
+            println!("Matching features...")
+        };
+        let pairs = if id_field.is_empty() {
+            match_by_proximity(&input1, &input2, search_distance)
+        } else {
+            match_by_id(&input1, &input2, &id_field)
+        };
+        reporter.check_cancelled()?;
+
+        let mut matched1 = vec![false; input1.num_records];
+        let mut matched2 = vec![false; input2.num_records];
+        let mut num_geometry_differing = 0;
+        let mut num_attributes_differing = 0;
+        let mut max_hausdorff = 0f64;
+        let mut max_hausdorff_pair: Option<(usize, usize)> = None;
+        let mut sum_hausdorff = 0f64;
+        let mut num_measured = 0;
+        let mut field_differences: HashMap<String, usize> = HashMap::new();
+        let mut differing_features = vec![];
+        let mut num_differing_features = 0;
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for (n, &(rec1, rec2)) in pairs.iter().enumerate() {
+            matched1[rec1] = true;
+            matched2[rec2] = true;
+            let g1 = input1.get_record(rec1);
+            let g2 = input2.get_record(rec2);
+            let hausdorff = hausdorff_distance(&geometry_parts(g1), &geometry_parts(g2));
+            let geometry_differs = !(hausdorff <= tolerance);
+            if geometry_differs {
+                num_geometry_differing += 1;
+            }
+            if hausdorff.is_finite() {
+                num_measured += 1;
+                sum_hausdorff += hausdorff;
+                if hausdorff > max_hausdorff || max_hausdorff_pair.is_none() {
+                    max_hausdorff = hausdorff;
+                    max_hausdorff_pair = Some((rec1, rec2));
+                }
+            }
+
+            let mut values = Map::new();
+            for field in &common_fields {
+                let v1 = input1.attributes.get_value(rec1, field);
+                let v2 = input2.attributes.get_value(rec2, field);
+                if !values_equal(&v1, &v2, attribute_tolerance) {
+                    *field_differences.entry(field.clone()).or_insert(0) += 1;
+                    values.insert(field.clone(), json!([field_json(&v1), field_json(&v2)]));
+                }
+            }
+            if !values.is_empty() {
+                num_attributes_differing += 1;
+            }
+
+            if geometry_differs || !values.is_empty() {
+                num_differing_features += 1;
+                if differing_features.len() < MAX_LISTED_FEATURES {
+                    let num = if hausdorff.is_finite() { json!(hausdorff) } else { Value::Null };
+                    differing_features.push(json!({
+                        "record1": rec1 + 1,
+                        "record2": rec2 + 1,
+                        "hausdorff_distance": num,
+                        "attributes": values,
+                    }));
+                }
+            }
+
+            reporter.check_cancelled()?;
+            if print_messages {
+                progress = (100.0_f64 * n as f64 / (pairs.len() - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Comparing features", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let unmatched = |matched: &[bool]| -> Vec<usize> {
+            matched.iter().enumerate().filter(|(_, m)| !**m).map(|(i, _)| i + 1).collect()
+        };
+        let unmatched1 = unmatched(&matched1);
+        let unmatched2 = unmatched(&matched2);
+        let identical = num_differing_features == 0
+            && unmatched1.is_empty()
+            && unmatched2.is_empty()
+            && header_differences.is_empty();
+
+        let max_location = match max_hausdorff_pair {
+            Some((rec1, rec2)) => json!({ "record1": rec1 + 1, "record2": rec2 + 1 }),
+            None => Value::Null,
+        };
+        let report = json!({
+            "identical": identical,
+            "input1": input1_file,
+            "input2": input2_file,
+            "matched_by": if id_field.is_empty() { "proximity".to_string() } else { format!("field {}", id_field) },
+            "tolerance": tolerance,
+            "attribute_tolerance": attribute_tolerance,
+            "header_differences": header_differences,
+            "features": {
+                "num_matched": pairs.len(),
+                "num_unmatched1": unmatched1.len(),
+                "num_unmatched2": unmatched2.len(),
+                "num_differing": num_differing_features,
+                "num_geometry_differing": num_geometry_differing,
+                "num_attributes_differing": num_attributes_differing,
+            },
+            "geometry": {
+                "max_hausdorff_distance": if max_hausdorff_pair.is_some() { json!(max_hausdorff) } else { Value::Null },
+                "max_hausdorff_distance_features": max_location,
+                "mean_hausdorff_distance": if num_measured > 0 { json!(sum_hausdorff / num_measured as f64) } else { Value::Null },
+            },
+            "attribute_differences": field_differences,
+            "differing_features": differing_features,
+            "unmatched1": unmatched1.iter().take(MAX_LISTED_FEATURES).collect::<Vec<_>>(),
+            "unmatched2": unmatched2.iter().take(MAX_LISTED_FEATURES).collect::<Vec<_>>(),
+        });
+        let json_str = match serde_json::to_string_pretty(&report) {
+            Ok(s) => s,
+            Err(e) => return Err(Error::new(ErrorKind::Other, format!("{}", e))),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        match output_file {
+            Some(output_file) => {
+                let mut f = File::create(&output_file)?;
+                f.write_all(json_str.as_bytes())?;
+                if verbose {
+                    if identical {
+                        println!("The vector layers are identical.");
+                    } else {
+                        println!("The vector layers differ; see {} for details.", output_file);
+                    }
+                    println!(
+                        "{}",
+                        &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+                    );
+                }
+            }
+            None => println!("{}", json_str),
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the parts of a geometry, with each point of a multipoint as a part of its own.
+fn geometry_parts(geometry: &ShapefileGeometry) -> Vec<&[Point2D]> {
+    match geometry.shape_type.base_shape_type() {
+        ShapeType::Point | ShapeType::MultiPoint => {
+            geometry.points.iter().map(|p| std::slice::from_ref(p)).collect()
+        }
+        _ => {
+            let num_parts = geometry.parts.len();
+            (0..num_parts)
+                .map(|i| {
+                    let start = geometry.parts[i] as usize;
+                    let end = if i < num_parts - 1 {
+                        geometry.parts[i + 1] as usize
+                    } else {
+                        geometry.points.len()
+                    };
+                    &geometry.points[start..end]
+                })
+                .collect()
+        }
+    }
+}
+
+/// Matches the features with the same values in an ID field, in the order in which they occur
+/// when values are repeated. Features with null IDs aren't matched.
+fn match_by_id(input1: &Shapefile, input2: &Shapefile, id_field: &str) -> Vec<(usize, usize)> {
+    let mut records2: HashMap<String, Vec<usize>> = HashMap::new();
+    for rec in (0..input2.num_records).rev() {
+        let id = input2.attributes.get_value(rec, id_field);
+        if id != FieldData::Null {
+            records2.entry(id.to_string()).or_insert(vec![]).push(rec);
+        }
+    }
+    let mut pairs = vec![];
+    for rec1 in 0..input1.num_records {
+        let id = input1.attributes.get_value(rec1, id_field);
+        if id == FieldData::Null {
+            continue;
+        }
+        if let Some(rec2) = records2.get_mut(&id.to_string()).and_then(|r| r.pop()) {
+            pairs.push((rec1, rec2));
+        }
+    }
+    pairs
+}
+
+/// Matches features by the distances between their centres, pairing the closest features first,
+/// among the nearest few features of the second layer to each feature of the first.
+fn match_by_proximity(input1: &Shapefile, input2: &Shapefile, search_distance: f64) -> Vec<(usize, usize)> {
+    let centre = |geometry: &ShapefileGeometry| -> Option<[f64; 2]> {
+        if geometry.points.is_empty() {
+            return None;
+        }
+        let n = geometry.points.len() as f64;
+        let x = geometry.points.iter().map(|p| p.x).sum::<f64>() / n;
+        let y = geometry.points.iter().map(|p| p.y).sum::<f64>() / n;
+        Some([x, y])
+    };
+    let dimensions = 2;
+    let capacity_per_node = 64;
+    let mut tree = KdTree::with_capacity(dimensions, capacity_per_node);
+    for rec in 0..input2.num_records {
+        if let Some(c) = centre(input2.get_record(rec)) {
+            tree.add(c, rec).unwrap();
+        }
+    }
+    let num_candidates = 8.min(input2.num_records);
+    let mut candidates = vec![];
+    if num_candidates == 0 {
+        return vec![];
+    }
+    for rec1 in 0..input1.num_records {
+        if let Some(c) = centre(input1.get_record(rec1)) {
+            if let Ok(nearest) = tree.nearest(&c, num_candidates, &squared_euclidean) {
+                for (dist_sqrd, &rec2) in nearest {
+                    let dist = dist_sqrd.sqrt();
+                    if dist <= search_distance {
+                        candidates.push((dist, rec1, rec2));
+                    }
+                }
+            }
+        }
+    }
+    candidates.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then((a.1, a.2).cmp(&(b.1, b.2)))
+    });
+    let mut matched1 = vec![false; input1.num_records];
+    let mut matched2 = vec![false; input2.num_records];
+    let mut pairs = vec![];
+    for (_, rec1, rec2) in candidates {
+        if !matched1[rec1] && !matched2[rec2] {
+            matched1[rec1] = true;
+            matched2[rec2] = true;
+            pairs.push((rec1, rec2));
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+fn values_equal(v1: &FieldData, v2: &FieldData, tolerance: f64) -> bool {
+    let numeric = |v: &FieldData| match v {
+        FieldData::Int(i) => Some(*i as f64),
+        FieldData::Real(r) => Some(*r),
+        _ => None,
+    };
+    match (numeric(v1), numeric(v2)) {
+        (Some(a), Some(b)) => a == b || (a - b).abs() <= tolerance,
+        _ => v1 == v2,
+    }
+}
+
+fn field_json(value: &FieldData) -> Value {
+    match value {
+        FieldData::Int(i) => json!(i),
+        FieldData::Real(r) if r.is_finite() => json!(r),
+        FieldData::Bool(b) => json!(b),
+        FieldData::Null => Value::Null,
+        _ => json!(value.to_string()),
+    }
+}
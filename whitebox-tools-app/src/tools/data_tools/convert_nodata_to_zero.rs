@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to change the value within the grid cells of a raster file (`--input`) that contain
 /// NoData to zero. The most common reason for using this tool is to change the background region of a raster
@@ -121,39 +120,11 @@ impl WhiteboxTool for ConvertNodataToZero {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -166,15 +137,8 @@ impl WhiteboxTool for ConvertNodataToZero {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            // Windows now takes forward slash in directory names.
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Arc::new(Raster::new(&input_file, "r")?);
 
@@ -189,27 +153,25 @@ impl WhiteboxTool for ConvertNodataToZero {
         let mut output = Raster::initialize_using_file(&output_file, &input);
         output.configs.nodata = -32768f64; // make sure that the output image doesn't use a zero-valued nodata.
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        if input[(row, col)] != nodata {
+                        if !is_nodata(input[(row, col)], nodata) {
                             data[col as usize] = input[(row, col)];
                         } else {
                             data[col as usize] = 0.0f64;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -217,10 +179,11 @@ impl WhiteboxTool for ConvertNodataToZero {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -9,7 +9,7 @@ License: MIT
 use whitebox_raster::*;
 use crate::tools::*;
 use std::env;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool converts raster data from one format to another. It determines input/output raster
@@ -117,39 +117,11 @@ impl WhiteboxTool for ConvertRasterFormat {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -162,14 +134,8 @@ impl WhiteboxTool for ConvertRasterFormat {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -179,10 +145,19 @@ impl WhiteboxTool for ConvertRasterFormat {
         let start = Instant::now();
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
-        println!("Initializing the output raster...");
-        match output.set_data_from_raster(&input) {
-            Ok(_) => (), // do nothings
-            Err(err) => return Err(err),
+        let rows = input.configs.rows as isize;
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for row in 0..rows {
+            output.set_row_data(row, input.get_row_data(row));
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
         }
         drop(input);
 
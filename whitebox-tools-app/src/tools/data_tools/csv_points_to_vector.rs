@@ -151,81 +151,20 @@ impl WhiteboxTool for CsvPointsToVector {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         // let mut field_definitions = String::new();
-        let mut x_field = 0;
-        let mut y_field = 1;
+        let x_field = parsed.get_usize("xfield")?.unwrap_or(0);
+        let y_field = parsed.get_usize("yfield")?.unwrap_or(1);
         let mut epsg = 0u16;
         let mut projection_set = false;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-xfield" {
-                x_field = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-yfield" {
-                y_field = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-epsg" {
-                epsg = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as u16
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as u16
-                };
-                projection_set = true;
-            }
+        if let Some(value) = parsed.get_i64("epsg") {
+            epsg = value as u16;
+            projection_set = true;
         }
 
         if verbose {
@@ -239,18 +178,12 @@ impl WhiteboxTool for CsvPointsToVector {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
         // File strings need a full directory
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let start = Instant::now();
 
@@ -279,6 +212,7 @@ impl WhiteboxTool for CsvPointsToVector {
         let mut field_lengths: Vec<u8> = vec![];
         let mut field_precision: Vec<u8> = vec![];
         for line in f.lines() {
+            reporter.check_cancelled()?;
             let line_unwrapped = line.unwrap();
             if !line_unwrapped.trim().is_empty() {
                 let mut line_split = line_unwrapped.split(delimiter);
@@ -437,10 +371,11 @@ impl WhiteboxTool for CsvPointsToVector {
                 .attributes
                 .add_record(data[record_num].clone(), false);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (rec_num + 1) as f64 / data.len() as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
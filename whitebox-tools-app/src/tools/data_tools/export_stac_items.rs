@@ -0,0 +1,424 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::stac::*;
+use crate::tools::provenance::{hash_file, read_provenance, sidecar_file_name, Provenance};
+use crate::tools::*;
+use chrono::prelude::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+const PROCESSING_EXTENSION: &str = "https://stac-extensions.github.io/processing/v1.1.0/schema.json";
+const PROJECTION_EXTENSION: &str = "https://stac-extensions.github.io/projection/v1.1.0/schema.json";
+const FILE_EXTENSION: &str = "https://stac-extensions.github.io/file/v2.1.0/schema.json";
+
+/// This tool describes a set of files, typically the outputs of other tools, as a STAC
+/// (SpatioTemporal Asset Catalog), so that they can be published, indexed, or browsed with STAC
+/// clients. One STAC Item is written for each input file (`--inputs`), alongside the output catalog
+/// file (`--output`, e.g. `catalog.json`), and named after the input file, e.g. `slope.json` for
+/// `slope.tif`. If a collection ID (`--collection`) is specified, the output is a STAC Collection,
+/// whose spatial and temporal extents cover those of its items, rather than a Catalog. An optional
+/// `--description` is used for the catalog or collection.
+///
+/// The footprint (geometry) and bounding box of each item are those of the raster or vector file,
+/// reprojected to geographic coordinates (WGS84). Files in geographic, Web Mercator, or WGS84 and NAD83
+/// UTM coordinates are supported; items describing other files, or files that are neither rasters
+/// nor vectors, have no geometry. The EPSG code, native bounding box and, for rasters, the grid shape
+/// and transform of each file are recorded using the STAC projection extension, and the file's size
+/// and SHA-256 checksum using the file extension.
+///
+/// When an input file has a provenance sidecar (`.provenance.json`, see the `provenance` setting)
+/// that matches its contents, the item's date and time are those of the tool run that created the
+/// file, its processing lineage (the tool and parameters) and the software version are recorded
+/// using the STAC processing extension, and the sidecar, which holds the full processing history of
+/// the file, is included as a `metadata` asset. Items of input files that were created from other
+/// input files are connected to the items of those files by `derived_from` links. The date and time
+/// of other files is their modification time.
+///
+/// Asset hrefs are relative to the catalog for files within the catalog's directory, and are
+/// otherwise the paths of the files.
+///
+/// # See Also
+/// `StacSearch`
+pub struct ExportStacItems {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExportStacItems {
+    pub fn new() -> ExportStacItems {
+        // public constructor
+        let name = "ExportStacItems".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Writes STAC Items describing files, with their processing history, and a STAC Catalog or Collection."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input files, e.g. raster and vector tool outputs.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Catalog File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output STAC catalog (or collection) JSON file, e.g. catalog.json.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Collection ID".to_owned(),
+            flags: vec!["--collection".to_owned()],
+            description: "Collection ID; if specified, a STAC Collection is written rather than a Catalog (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Description".to_owned(),
+            flags: vec!["--description".to_owned()],
+            description: "Description of the catalog or collection (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i='dem_filled.tif;slope.tif;streams.shp' -o=catalog.json --collection=terrain",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ExportStacItems {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExportStacItems {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_list("inputs");
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let collection_id = parsed.get_string("collection").unwrap_or_default();
+        let description = parsed.get_string("description").unwrap_or_default();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut files: Vec<String> = vec![];
+        for f in &input_files {
+            files.push(resolve_input_path(f, working_directory)?);
+        }
+        if files.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one input file must be specified.",
+            ));
+        }
+        output_file = resolve_path(&output_file, working_directory);
+        let catalog_dir = match path::Path::new(&output_file).parent() {
+            Some(p) => p.to_path_buf(),
+            None => path::PathBuf::from(working_directory),
+        };
+        let catalog_name = path::Path::new(&output_file)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("catalog.json")
+            .to_string();
+        let collection_id = collection_id.trim().to_string();
+
+        let start = Instant::now();
+
+        // Assign each file a unique item ID, from its file name.
+        let mut item_ids: Vec<String> = vec![];
+        for file in &files {
+            let p = path::Path::new(file);
+            let stem = p.file_stem().and_then(|s| s.to_str()).unwrap_or("item").to_string();
+            let mut id = stem.clone();
+            if item_ids.contains(&id) || id == catalog_name.trim_end_matches(".json") {
+                id = format!("{}_{}", stem, extension(file));
+            }
+            let mut n = 2;
+            while item_ids.contains(&id) {
+                id = format!("{}_{}", stem, n);
+                n += 1;
+            }
+            item_ids.push(id);
+        }
+        let id_of_file: HashMap<String, String> = files
+            .iter()
+            .cloned()
+            .zip(item_ids.iter().cloned())
+            .collect();
+
+        let mut items: Vec<Value> = vec![];
+        for (n, file) in files.iter().enumerate() {
+            reporter.check_cancelled()?;
+            if verbose {
+                println!("Describing {}...", file);
+            }
+            let sha256 = hash_file(file)?;
+            // A sidecar is only trusted if the file still matches the output that it describes.
+            let provenance: Option<Provenance> = read_provenance(file)
+                .filter(|p| p.outputs.iter().any(|o| o.sha256.as_ref() == Some(&sha256)));
+            let metadata = fs::metadata(file)?;
+
+            let modified = metadata
+                .modified()
+                .map(DateTime::<Utc>::from)
+                .unwrap_or_else(|_| Utc::now());
+            let datetime = provenance
+                .as_ref()
+                .and_then(|p| DateTime::parse_from_rfc3339(&p.timestamp).ok())
+                .map(|d| d.with_timezone(&Utc))
+                .unwrap_or(modified);
+
+            let mut extensions = vec![FILE_EXTENSION];
+            let mut properties = json!({
+                "datetime": datetime.to_rfc3339_opts(SecondsFormat::Secs, true),
+            });
+            let mut geometry = Value::Null;
+            let mut bbox = Value::Null;
+            if let Some(extent) = read_extent(file)? {
+                extensions.push(PROJECTION_EXTENSION);
+                properties["proj:epsg"] = if extent.epsg > 0 { json!(extent.epsg) } else { Value::Null };
+                properties["proj:bbox"] = json!(extent.bbox);
+                if let Some(shape) = extent.shape {
+                    properties["proj:shape"] = json!(shape);
+                }
+                if let Some(transform) = extent.transform {
+                    properties["proj:transform"] = json!(transform);
+                }
+                if let Some(geographic_bbox) = extent.geographic_bbox() {
+                    bbox = json!(geographic_bbox);
+                    geometry = json!({
+                        "type": "Polygon",
+                        "coordinates": [extent.footprint.unwrap()],
+                    });
+                }
+            }
+
+            let mut data_asset = json!({
+                "href": relative_href(file, &catalog_dir),
+                "roles": ["data"],
+                "file:size": metadata.len(),
+                // a multihash: 0x12 identifies SHA-256 and 0x20 its length, 32 bytes
+                "file:checksum": format!("1220{}", sha256),
+            });
+            if let Some(media_type) = media_type(file) {
+                data_asset["type"] = json!(media_type);
+            }
+            let mut assets = json!({ "data": data_asset });
+
+            let mut links = vec![
+                json!({ "rel": "root", "href": format!("./{}", catalog_name), "type": "application/json" }),
+                json!({ "rel": "parent", "href": format!("./{}", catalog_name), "type": "application/json" }),
+            ];
+            if !collection_id.is_empty() {
+                links.push(json!({ "rel": "collection", "href": format!("./{}", catalog_name), "type": "application/json" }));
+            }
+
+            if let Some(p) = &provenance {
+                extensions.push(PROCESSING_EXTENSION);
+                let parameters: Vec<String> = p
+                    .parameters
+                    .iter()
+                    .map(|(flag, value)| format!("{}='{}'", flag, value))
+                    .collect();
+                properties["processing:lineage"] = json!(format!(
+                    "Created by the {} tool with {}",
+                    p.tool,
+                    parameters.join(" ")
+                ));
+                properties["processing:software"] = json!({ "WhiteboxTools": p.version });
+                properties["created"] = properties["datetime"].clone();
+                assets["provenance"] = json!({
+                    "href": relative_href(&sidecar_file_name(file), &catalog_dir),
+                    "type": "application/json",
+                    "roles": ["metadata"],
+                    "title": "Processing history",
+                });
+                for input in &p.inputs {
+                    if let Some(id) = id_of_file.get(&input.file) {
+                        links.push(json!({
+                            "rel": "derived_from",
+                            "href": format!("./{}.json", id),
+                            "type": "application/geo+json",
+                        }));
+                    }
+                }
+            }
+
+            let mut item = json!({
+                "type": "Feature",
+                "stac_version": STAC_VERSION,
+                "stac_extensions": extensions,
+                "id": item_ids[n],
+                "geometry": geometry,
+                "properties": properties,
+                "links": links,
+                "assets": assets,
+            });
+            if !bbox.is_null() {
+                item["bbox"] = bbox;
+            }
+            if !collection_id.is_empty() {
+                item["collection"] = json!(collection_id);
+            }
+            items.push(item);
+
+            if verbose {
+                let progress = (100.0_f64 * (n + 1) as f64 / files.len() as f64) as usize;
+                reporter.progress("Progress", progress);
+            }
+        }
+
+        // Write the items and the catalog or collection that links them.
+        let mut catalog_links = vec![
+            json!({ "rel": "root", "href": format!("./{}", catalog_name), "type": "application/json" }),
+        ];
+        for (item, id) in items.iter().zip(item_ids.iter()) {
+            let item_file = catalog_dir.join(format!("{}.json", id));
+            fs::write(&item_file, serde_json::to_string_pretty(item)?)?;
+            catalog_links.push(json!({
+                "rel": "item",
+                "href": format!("./{}.json", id),
+                "type": "application/geo+json",
+            }));
+        }
+
+        let catalog = if collection_id.is_empty() {
+            json!({
+                "type": "Catalog",
+                "stac_version": STAC_VERSION,
+                "id": catalog_name.trim_end_matches(".json"),
+                "description": if description.is_empty() { "Created by WhiteboxTools".to_string() } else { description },
+                "links": catalog_links,
+            })
+        } else {
+            let mut spatial = [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY];
+            for item in &items {
+                if let Some(b) = item["bbox"].as_array() {
+                    for i in 0..4 {
+                        let v = b[i].as_f64().unwrap_or(0f64);
+                        spatial[i] = if i < 2 { spatial[i].min(v) } else { spatial[i].max(v) };
+                    }
+                }
+            }
+            let spatial = if spatial[0].is_finite() {
+                spatial
+            } else {
+                [-180f64, -90f64, 180f64, 90f64]
+            };
+            let mut datetimes: Vec<&str> = items
+                .iter()
+                .filter_map(|item| item["properties"]["datetime"].as_str())
+                .collect();
+            // all are UTC and in the same format, and so sort chronologically
+            datetimes.sort();
+            json!({
+                "type": "Collection",
+                "stac_version": STAC_VERSION,
+                "id": collection_id,
+                "description": if description.is_empty() { "Created by WhiteboxTools".to_string() } else { description },
+                "license": "proprietary",
+                "extent": {
+                    "spatial": { "bbox": [spatial] },
+                    "temporal": { "interval": [[datetimes.first(), datetimes.last()]] },
+                },
+                "links": catalog_links,
+            })
+        };
+        fs::write(&output_file, serde_json::to_string_pretty(&catalog)?)?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Output file written");
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
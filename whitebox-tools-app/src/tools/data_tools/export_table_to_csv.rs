@@ -12,7 +12,7 @@ use std::env;
 use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
-use std::io::{BufWriter, Error, ErrorKind};
+use std::io::{BufWriter, Error};
 use std::path;
 
 /// This tool can be used to export a vector's attribute table to a comma separated values (CSV)
@@ -65,7 +65,7 @@ impl ExportTableToCsv {
             flags: vec!["--headers".to_owned()],
             description: "Export field names as file header?".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -131,45 +131,12 @@ impl WhiteboxTool for ExportTableToCsv {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut headers = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-headers" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    headers = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let headers = parsed.get_bool("headers");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -182,17 +149,11 @@ impl WhiteboxTool for ExportTableToCsv {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -251,11 +212,12 @@ impl WhiteboxTool for ExportTableToCsv {
             s.push_str("\n");
             writer.write_all(s.as_bytes())?;
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (vector_data.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Writing attributes: {}%", progress);
+                    reporter.progress("Writing attributes", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,268 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::structures::HexGrid;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool converts a raster on a hexagonal grid (`--input`), such as those created by `HexToSquareGrid`
+/// and `HexFlowAccumulation`, back onto a square grid, e.g. for display or for use with other tools. The
+/// input must use the 'odd-r' offset layout of the hexagonal grid tools, in which the rows of the raster are
+/// spaced at sqrt(3) / 2 times the column spacing and the cells of odd rows are shifted half a cell east.
+/// Each output cell takes the value of the hexagon that contains its centre.
+///
+/// The output grid is defined either by a cell size (`--cell_size`), which defaults to the hexagon width,
+/// and the extent of the input, or by an existing raster (`--base`).
+///
+/// # See Also
+/// `HexToSquareGrid`, `HexFlowAccumulation`
+pub struct HexToSquareGrid {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl HexToSquareGrid {
+    pub fn new() -> HexToSquareGrid {
+        // public constructor
+        let name = "HexToSquareGrid".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Converts a hexagonal grid raster in the offset (odd-r) layout onto a square grid.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input hexagonal grid raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Output cell size; defaults to the hexagon width. Ignored if a base raster is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optional raster defining the output grid.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=flow_accum_hex.tif -o=flow_accum.tif --cell_size=10.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        HexToSquareGrid {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for HexToSquareGrid {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut cell_size = parsed.get_f64("cell_size").unwrap_or(0f64);
+        let base_file = parsed.get_string("base").unwrap_or_default();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let grid = HexGrid::from_raster_grid(
+            input.configs.rows,
+            input.configs.columns,
+            input.configs.west,
+            input.configs.north,
+            input.configs.resolution_x,
+            input.configs.resolution_y,
+        )
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "The input raster is not a hexagonal grid; its row spacing should be sqrt(3)/2 times its column spacing. Use SquareToHexGrid to create hexagonal grids.",
+            )
+        })?;
+
+        let start = Instant::now();
+
+        let mut configs = input.configs.clone();
+        // the grid differs from the input's, so its GeoTIFF georeferencing tags don't apply
+        configs.model_tiepoint = vec![];
+        configs.model_pixel_scale = [0f64; 3];
+        configs.model_transformation = [0f64; 16];
+        if !base_file.is_empty() {
+            let base = Raster::new(&resolve_input_path(&base_file, working_directory)?, "r")?;
+            configs.rows = base.configs.rows;
+            configs.columns = base.configs.columns;
+            configs.north = base.configs.north;
+            configs.south = base.configs.south;
+            configs.east = base.configs.east;
+            configs.west = base.configs.west;
+            configs.resolution_x = base.configs.resolution_x;
+            configs.resolution_y = base.configs.resolution_y;
+        } else {
+            if cell_size <= 0f64 {
+                cell_size = grid.width;
+            }
+            configs.rows = ((configs.north - configs.south) / cell_size).ceil() as usize;
+            configs.columns = ((configs.east - configs.west) / cell_size).ceil() as usize;
+            configs.south = configs.north - configs.rows as f64 * cell_size;
+            configs.east = configs.west + configs.columns as f64 * cell_size;
+            configs.resolution_x = cell_size;
+            configs.resolution_y = cell_size;
+        }
+        configs.metadata = vec![];
+        let rows = configs.rows as isize;
+        let columns = configs.columns as isize;
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        let nodata = input.configs.nodata;
+        let out_nodata = output.configs.nodata;
+
+        for row in 0..rows {
+            let y = output.get_y_from_row(row);
+            for col in 0..columns {
+                if let Some((r, c)) = grid.cell_containing(output.get_x_from_column(col), y) {
+                    let z = input.get_value(r, c);
+                    output.set_value(row, col, if is_nodata(z, nodata) { out_nodata } else { z });
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
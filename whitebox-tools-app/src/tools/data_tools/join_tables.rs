@@ -75,7 +75,7 @@ impl JoinTables {
 
         parameters.push(ToolParameter {
             name: "Primary Key Field".to_owned(),
-            flags: vec!["--pkey".to_owned()],
+            flags: vec!["--primary_key".to_owned(), "--pkey".to_owned()],
             description: "Primary key field.".to_owned(),
             parameter_type: ParameterType::VectorAttributeField(
                 AttributeType::Any,
@@ -99,7 +99,7 @@ impl JoinTables {
 
         parameters.push(ToolParameter {
             name: "Foreign Key Field".to_owned(),
-            flags: vec!["--fkey".to_owned()],
+            flags: vec!["--foreign_key".to_owned(), "--fkey".to_owned()],
             description: "Foreign key field.".to_owned(),
             parameter_type: ParameterType::VectorAttributeField(
                 AttributeType::Any,
@@ -183,61 +183,14 @@ impl WhiteboxTool for JoinTables {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input1_file = String::new();
-        let mut primary_key = String::new();
-        let mut input2_file = String::new();
-        let mut foreign_key = String::new();
-        let mut import_field = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i1" || flag_val == "-input1" {
-                input1_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-primary_key" || flag_val == "-pkey" {
-                primary_key = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-i2" || flag_val == "-input2" {
-                input2_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-foreign_key" || flag_val == "-fkey" {
-                foreign_key = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-import_field" {
-                import_field = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input1_file = parsed.get_string("input1").unwrap_or_default();
+        let primary_key = parsed.get_string("pkey").unwrap_or_default();
+        let mut input2_file = parsed.get_string("input2").unwrap_or_default();
+        let foreign_key = parsed.get_string("fkey").unwrap_or_default();
+        let import_field = parsed.get_string("import_field").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -250,17 +203,11 @@ impl WhiteboxTool for JoinTables {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input1_file.contains(&sep) && !input1_file.contains("/") {
-            input1_file = format!("{}{}", working_directory, input1_file);
-        }
-        if !input2_file.contains(&sep) && !input2_file.contains("/") {
-            input2_file = format!("{}{}", working_directory, input2_file);
-        }
+        input1_file = resolve_input_path(&input1_file, working_directory)?;
+        input2_file = resolve_input_path(&input2_file, working_directory)?;
 
         if verbose {
             println!("Reading data...")
@@ -324,6 +271,7 @@ impl WhiteboxTool for JoinTables {
         let (mut pkey_value, mut fkey_value): (String, String);
         let mut data_map = HashMap::new();
         for record_num in 0..input2.num_records {
+            reporter.check_cancelled()?;
             fkey_value = input2
                 .attributes
                 .get_value(record_num, &foreign_key)
@@ -368,11 +316,12 @@ impl WhiteboxTool for JoinTables {
 
             output.attributes.add_record(atts, false);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num + 1) as f64 / input1.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
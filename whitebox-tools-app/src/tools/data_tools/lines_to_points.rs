@@ -0,0 +1,347 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::structures::Point2D;
+use crate::tools::*;
+use whitebox_vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool converts vector polylines (or polygon boundaries) into vector points. By default, each
+/// vertex of the input features becomes an output point. Alternatively, when a `--interval` distance is
+/// specified, points are instead placed at regular intervals along each line part, starting at the
+/// first vertex, with an additional point placed at the end of each part. This makes the tool useful
+/// for creating stations along routes, e.g. for sampling raster values along a profile.
+///
+/// Each output point records the FID of the feature it was derived from (PARENT_FID), the part number
+/// within that feature (PART_NUM), and its distance along the feature (DIST), measured from the start
+/// of the first part. Distances are not accumulated across the gaps between parts. The attributes of
+/// the parent feature are also copied to each output point. If the input lines contain z-values
+/// and/or measures, these are carried over to the output points, using linear interpolation for points
+/// that are placed between vertices.
+///
+/// # See Also
+/// `PointsToLines`, `ExtractNodes`
+pub struct LinesToPoints {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LinesToPoints {
+    pub fn new() -> LinesToPoints {
+        // public constructor
+        let name = "LinesToPoints".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Converts vector polylines to points at vertices or regular intervals, with distance-along attributes."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Lines File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector polyline or polygon file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::LineOrPolygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Points File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector points file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Point Spacing Interval".to_owned(),
+            flags: vec!["--interval".to_owned()],
+            description: "Distance between output points along lines; vertices are output if unspecified (optional).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=streams.shp -o=stations.shp --interval=100.0",
+            short_exe, name
+        ).replace("*", &sep);
+
+        LinesToPoints {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LinesToPoints {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let interval = parsed.get_f64("interval").unwrap_or(0f64);
+
+        // read the arguments
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        // make sure the input vector file is of polyline or polygon type
+        let base_type = input.header.shape_type.base_shape_type();
+        if base_type != ShapeType::PolyLine && base_type != ShapeType::Polygon {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of a POLYLINE or POLYGON base shape type.",
+            ));
+        }
+
+        let output_type = match input.header.shape_type.dimension() {
+            ShapeTypeDimension::Z => ShapeType::PointZ,
+            ShapeTypeDimension::Measure => ShapeType::PointM,
+            _ => ShapeType::Point,
+        };
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, output_type, false)?;
+        let new_fields = ["FID", "PARENT_FID", "PART_NUM", "DIST"];
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 8u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("PARENT_FID", FieldDataType::Int, 8u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("PART_NUM", FieldDataType::Int, 5u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("DIST", FieldDataType::Real, 14u8, 4u8));
+        let mut copied_fields: Vec<usize> = vec![];
+        for i in 0..input.attributes.get_num_fields() {
+            let field = input.attributes.get_field(i);
+            if !new_fields
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(field.name.trim()))
+            {
+                output.attributes.add_field(field);
+                copied_fields.push(i);
+            }
+        }
+
+        let mut fid = 1i32;
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            if record.shape_type == ShapeType::Null {
+                continue;
+            }
+            let parent_atts = input.attributes.get_record(record_num);
+            let has_z = record.has_z_data();
+            let has_m = record.has_m_data();
+            let mut dist_offset = 0f64;
+            for part in 0..record.num_parts as usize {
+                let part_start = record.parts[part] as usize;
+                let part_end = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize - 1
+                } else {
+                    record.num_points as usize - 1
+                };
+
+                // Each station is the vertex index at the start of its segment, the fraction
+                // along that segment, and the distance along the feature.
+                let mut stations: Vec<(usize, f64, f64)> = vec![];
+                let mut part_length = 0f64;
+                if interval > 0f64 {
+                    let mut next_station = 0f64;
+                    for i in part_start..part_end {
+                        let seg_length = record.points[i].distance(&record.points[i + 1]);
+                        while next_station <= part_length + seg_length && seg_length > 0f64 {
+                            let t = (next_station - part_length) / seg_length;
+                            stations.push((i, t, dist_offset + next_station));
+                            next_station += interval;
+                        }
+                        part_length += seg_length;
+                    }
+                    // always end with the last vertex of the part
+                    let last_dist = match stations.last() {
+                        Some(s) => s.2 - dist_offset,
+                        None => -1f64,
+                    };
+                    if part_length - last_dist > 1e-9 * part_length.max(1f64) {
+                        stations.push((part_end, 0f64, dist_offset + part_length));
+                    }
+                } else {
+                    for i in part_start..=part_end {
+                        if i > part_start {
+                            part_length += record.points[i - 1].distance(&record.points[i]);
+                        }
+                        stations.push((i, 0f64, dist_offset + part_length));
+                    }
+                }
+                dist_offset += part_length;
+
+                for (i, t, dist) in stations {
+                    let j = if t > 0f64 { i + 1 } else { i };
+                    let p = Point2D::new(
+                        record.points[i].x + t * (record.points[j].x - record.points[i].x),
+                        record.points[i].y + t * (record.points[j].y - record.points[i].y),
+                    );
+                    let m = if has_m {
+                        record.m_array[i] + t * (record.m_array[j] - record.m_array[i])
+                    } else {
+                        0f64
+                    };
+                    let mut sfg = ShapefileGeometry::new(output_type);
+                    match output_type {
+                        ShapeType::PointZ => {
+                            let z = if has_z {
+                                record.z_array[i] + t * (record.z_array[j] - record.z_array[i])
+                            } else {
+                                0f64
+                            };
+                            sfg.add_pointz(p, m, z);
+                        }
+                        ShapeType::PointM => sfg.add_pointm(p, m),
+                        _ => sfg.add_point(p),
+                    }
+                    output.add_record(sfg);
+
+                    let mut atts = vec![
+                        FieldData::Int(fid),
+                        FieldData::Int(record_num as i32 + 1),
+                        FieldData::Int(part as i32 + 1),
+                        FieldData::Real(dist),
+                    ];
+                    for &f in &copied_fields {
+                        atts.push(parent_atts[f].clone());
+                    }
+                    output.attributes.add_record(atts, false);
+                    fid += 1;
+                }
+            }
+
+            reporter.check_cancelled()?;
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
@@ -81,7 +81,7 @@ impl MergeTableWithCsv {
 
         parameters.push(ToolParameter {
             name: "Primary Key Field".to_owned(),
-            flags: vec!["--pkey".to_owned()],
+            flags: vec!["--primary_key".to_owned(), "--pkey".to_owned()],
             description: "Primary key field.".to_owned(),
             parameter_type: ParameterType::VectorAttributeField(
                 AttributeType::Any,
@@ -102,7 +102,7 @@ impl MergeTableWithCsv {
 
         parameters.push(ToolParameter {
             name: "Foreign Key Field".to_owned(),
-            flags: vec!["--fkey".to_owned()],
+            flags: vec!["--foreign_key".to_owned(), "--fkey".to_owned()],
             description: "Foreign key field.".to_owned(),
             parameter_type: ParameterType::VectorAttributeField(
                 AttributeType::Any,
@@ -114,7 +114,7 @@ impl MergeTableWithCsv {
 
         parameters.push(ToolParameter {
             name: "Imported Field".to_owned(),
-            flags: vec!["--import_field".to_owned()],
+            flags: vec!["--import".to_owned(), "--import_field".to_owned()],
             description: "Imported field (all fields will be imported if not specified)."
                 .to_owned(),
             parameter_type: ParameterType::VectorAttributeField(
@@ -187,61 +187,14 @@ impl WhiteboxTool for MergeTableWithCsv {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut primary_key = String::new();
-        let mut csv_file = String::new();
-        let mut foreign_key = String::new();
-        let mut import_field = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-primary_key" || flag_val == "-pkey" {
-                primary_key = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-csv" {
-                csv_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-foreign_key" || flag_val == "-fkey" {
-                foreign_key = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-import_field" || flag_val == "-import" {
-                import_field = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let primary_key = parsed.get_string("pkey").unwrap_or_default();
+        let mut csv_file = parsed.get_string("csv").unwrap_or_default();
+        let foreign_key = parsed.get_string("fkey").unwrap_or_default();
+        let import_field = parsed.get_string("import_field").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -254,17 +207,11 @@ impl WhiteboxTool for MergeTableWithCsv {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !csv_file.contains(&sep) && !csv_file.contains("/") {
-            csv_file = format!("{}{}", working_directory, csv_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        csv_file = resolve_input_path(&csv_file, working_directory)?;
 
         if verbose {
             println!("Reading data...")
@@ -290,6 +237,7 @@ impl WhiteboxTool for MergeTableWithCsv {
         let mut field_precision: Vec<u8> = vec![];
         let mut a: usize;
         for line in f.lines() {
+            reporter.check_cancelled()?;
             let line_unwrapped = line.unwrap();
             let mut line_split = line_unwrapped.split(delimiter);
             let mut line_vec = line_split.collect::<Vec<&str>>();
@@ -433,11 +381,12 @@ impl WhiteboxTool for MergeTableWithCsv {
 
             output.attributes.add_record(atts, false);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
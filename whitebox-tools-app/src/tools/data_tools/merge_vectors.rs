@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 01/10/2018
-Last Modified: 12/10/2018
+Last Modified: 16/10/2026
 License: MIT
 */
 
@@ -13,11 +13,17 @@ use std::io::{Error, ErrorKind};
 use std::path;
 
 /// Combines two or more input vectors of the same ShapeType creating a single, new output
-/// vector. Importantly, the attribute table of the output vector will contain the ubiquitous
-/// file-specific FID, the parent file name, the parent FID, and the list of attribute fields
-/// that are shared among each of the input files. For a field to be considered common
-/// between tables, it must have the same `name` and `field_type` (i.e. data type and
-/// precision).
+/// vector. Inputs may differ in whether they carry z-values or measures, e.g. a PolyLine and a
+/// PolyLineZ file may be merged, in which case the output is of the richest of the input types
+/// (z-values, then measures) and features lacking those values are given values of zero. Importantly, the attribute table of the output vector will contain the ubiquitous
+/// file-specific FID, the parent file name (`PARENT`), the parent FID (`PARENT_FID`), and the
+/// union of the attribute fields contained in each of the input files. The input files therefore
+/// do not need to share the same attribute schema. Fields are matched among tables by `name`
+/// (ignoring case). When matching fields differ in their data type, the output field is promoted
+/// to a type that is able to hold each of the input values; integer and floating-point fields
+/// are combined into a floating-point field, and any other combination of types is converted
+/// into a text field. Features from an input file that lacks one of the output fields will
+/// be assigned null values for that field.
 ///
 /// Overlapping features will not be identified nor handled in the merging. If you have
 /// significant areas of overlap, it is advisable to use one of the vector overlay tools
@@ -50,7 +56,7 @@ impl MergeVectors {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input Vector Files".to_string(),
-            flags: vec!["-i".to_owned(), "--inputs".to_string()],
+            flags: vec!["-i".to_owned(), "--input".to_owned(), "--inputs".to_owned()],
             description: "Input vector files.".to_string(),
             parameter_type: ParameterType::FileList(ParameterFileType::Vector(
                 VectorGeometryType::Any,
@@ -138,43 +144,14 @@ impl WhiteboxTool for MergeVectors {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files = String::new();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-inputs" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -191,9 +168,7 @@ impl WhiteboxTool for MergeVectors {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut cmd = input_files.split(";");
         let mut vec = cmd.collect::<Vec<&str>>();
@@ -208,28 +183,28 @@ impl WhiteboxTool for MergeVectors {
         }
 
         // We need to initialize output here, but in reality this can't be done
-        // until we know the size of rows and columns, which occurs during the first loop.
+        // until we know the ShapeType, which occurs during the first loop.
         let mut output: Shapefile = Shapefile::new(&output_file, ShapeType::Null)?;
         let mut read_first_file = false;
+        let mut output_shape_type = ShapeType::Null;
 
-        // It will be necessary to find which fields in the attribute tables are in
-        // common among the input files.
+        // The output attribute table contains the union of the fields found in each
+        // of the input files. Fields are matched by name (ignoring case) and, where
+        // the data types of matching fields differ, they are promoted to a type that
+        // can hold the values of each input.
         let mut in_files: Vec<String> = vec![];
-        let mut atts: Vec<AttributeField> = vec![AttributeField {
-            ..Default::default()
-        }];
+        let mut atts: Vec<AttributeField> = vec![];
+        let mut parent_field_length = 25usize;
 
         // The current program structure is not ideal because each of the input files
-        // are read twice; once to find the set of attributes shared among all of the
+        // are read twice; once to find the union of the attributes among all of the
         // inputs and a second time to fill the output file. I can't currently think
         // of a way to avoid this doubling of IO that doesn't involve storing each
         // vector in memory for the second pass, which would also be suboptimal.
         for value in vec {
             if !value.trim().is_empty() {
                 let mut input_file = value.trim().to_string();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
 
                 in_files.push(input_file.clone());
 
@@ -242,19 +217,6 @@ impl WhiteboxTool for MergeVectors {
                 if !read_first_file {
                     read_first_file = true;
 
-                    // Add the attributes. The strategy is to
-                    // initialize the attribute table with all
-                    // of the attributes of the first vector
-                    // file (excluding the FID), and then to
-                    // remove any attribute field that is not
-                    // found in any of the subsequent files.
-                    // let a = input.attributes.get_fields();
-                    for att in input.attributes.get_fields() {
-                        if att.name.to_lowercase() != "fid" {
-                            atts.push(att.clone());
-                        }
-                    }
-
                     // initialize the output
                     output = Shapefile::initialize_using_file(
                         &output_file,
@@ -262,29 +224,47 @@ impl WhiteboxTool for MergeVectors {
                         input.header.shape_type,
                         false,
                     )?;
-                } else {
-                    atts.intersection(input.attributes.get_fields());
+                    output_shape_type = input.header.shape_type;
                 }
 
-                if input.header.shape_type.base_shape_type() != output.header.shape_type.base_shape_type() {
+                if input.header.shape_type.base_shape_type() != output_shape_type.base_shape_type() {
                     return Err(Error::new(
                         ErrorKind::InvalidInput,
                         "Each of the input files must be of the same ShapeType.",
                     ));
                 }
+                output_shape_type = promote_shape_type(output_shape_type, input.header.shape_type);
+
+                for att in input.attributes.get_fields() {
+                    let name = att.name.to_lowercase();
+                    if name == "fid" || name == "parent" || name == "parent_fid" {
+                        continue;
+                    }
+                    match atts.iter().position(|a| a.name.to_lowercase() == name) {
+                        Some(index) => {
+                            atts[index] = promote_field(&atts[index], att);
+                        }
+                        None => atts.push(att.clone()),
+                    }
+                }
+
+                parent_field_length =
+                    parent_field_length.max(input.get_short_filename().replace(".shp", "").len());
             }
         }
 
         atts.insert(0, AttributeField::new("FID", FieldDataType::Int, 8u8, 0u8));
         atts.insert(
             1,
-            AttributeField::new("PARENT", FieldDataType::Text, 25u8, 0u8),
+            AttributeField::new("PARENT", FieldDataType::Text, parent_field_length.min(254) as u8, 0u8),
         );
         atts.insert(
             2,
             AttributeField::new("PARENT_FID", FieldDataType::Int, 8u8, 0u8),
         );
 
+        output.header.shape_type = output_shape_type;
+
         let num_atts = atts.len();
         for a in &atts {
             output.attributes.add_field(&(a.clone()));
@@ -295,8 +275,19 @@ impl WhiteboxTool for MergeVectors {
             let input = Shapefile::read(&input_file)?;
             let short_name = input.get_short_filename().replace(".shp", "");
 
+            // Which input field, if any, corresponds to each of the output fields?
+            let mut field_mapping: Vec<Option<usize>> = vec![None; num_atts];
+            for i in 3..num_atts {
+                let name = atts[i].name.to_lowercase();
+                field_mapping[i] = input
+                    .attributes
+                    .get_fields()
+                    .iter()
+                    .position(|a| a.name.to_lowercase() == name);
+            }
+
             for record_num in 0..input.num_records {
-                let record = input.get_record(record_num).clone();
+                let record = promote_record(input.get_record(record_num).clone(), output_shape_type);
                 output.add_record(record);
 
                 // attributes
@@ -306,18 +297,23 @@ impl WhiteboxTool for MergeVectors {
                 out_atts.push(FieldData::Text(short_name.clone())); // parent file name.
                 out_atts.push(FieldData::Int(record_num as i32 + 1)); // the record PARENT_FID
 
-                // Now the list of shared attributes.
+                // Now the union of attributes, with nulls for fields absent in this input.
+                let rec = input.attributes.get_record(record_num);
                 for i in 3..num_atts {
-                    out_atts.push(input.attributes.get_value(record_num, &(atts[i].name)))
+                    out_atts.push(match field_mapping[i] {
+                        Some(index) => convert_field_data(rec[index].clone(), &atts[i]),
+                        None => FieldData::Null,
+                    });
                 }
 
                 output.attributes.add_record(out_atts, false);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -345,3 +341,170 @@ impl WhiteboxTool for MergeVectors {
         Ok(())
     }
 }
+
+/// Ranks the dimensions of a ShapeType, from plain x-y coordinates, through measures, to z-values
+/// (which are optionally accompanied by measures).
+fn dimension_rank(shape_type: ShapeType) -> u8 {
+    match shape_type.dimension() {
+        ShapeTypeDimension::XY => 0,
+        ShapeTypeDimension::Measure => 1,
+        ShapeTypeDimension::Z => 2,
+    }
+}
+
+/// Returns the richer of two ShapeTypes sharing a base ShapeType.
+fn promote_shape_type(shape_type1: ShapeType, shape_type2: ShapeType) -> ShapeType {
+    if dimension_rank(shape_type2) > dimension_rank(shape_type1) {
+        shape_type2
+    } else {
+        shape_type1
+    }
+}
+
+/// Converts a record into the (possibly richer) ShapeType of the output, giving it z-values and
+/// measures of zero where the output requires them and the record lacks them.
+fn promote_record(mut record: ShapefileGeometry, shape_type: ShapeType) -> ShapefileGeometry {
+    if record.shape_type == shape_type || record.shape_type == ShapeType::Null {
+        return record;
+    }
+    let n = record.points.len();
+    let dimension = shape_type.dimension();
+    if dimension == ShapeTypeDimension::Z && !record.has_z_data() {
+        record.z_array = vec![0f64; n];
+        record.z_min = 0f64;
+        record.z_max = 0f64;
+    }
+    // PointZ records are always written with a measure.
+    if (dimension == ShapeTypeDimension::Measure || shape_type == ShapeType::PointZ) && !record.has_m_data() {
+        record.m_array = vec![0f64; n];
+        record.m_min = 0f64;
+        record.m_max = 0f64;
+    }
+    record.shape_type = shape_type;
+    record
+}
+
+fn is_numeric_field(field: &AttributeField) -> bool {
+    matches!(field.field_type, 'N' | 'F' | 'I' | 'O')
+}
+
+/// Returns a field that is capable of holding the values of two like-named fields.
+/// Numeric fields are widened to hold the larger number of decimals and fields
+/// of incompatible types are promoted to text.
+fn promote_field(field1: &AttributeField, field2: &AttributeField) -> AttributeField {
+    let mut ret = field1.clone();
+    ret.field_length = field1.field_length.max(field2.field_length);
+    if field1.field_type == field2.field_type {
+        ret.decimal_count = field1.decimal_count.max(field2.decimal_count);
+    } else if is_numeric_field(field1) && is_numeric_field(field2) {
+        ret.field_type = FieldDataType::Real.to_char();
+        ret.decimal_count = field1.decimal_count.max(field2.decimal_count);
+    } else {
+        ret.field_type = FieldDataType::Text.to_char();
+        ret.decimal_count = 0u8;
+        ret.field_length = ret.field_length.max(20u8);
+    }
+    if is_numeric_field(&ret) && ret.decimal_count > 0 {
+        ret.field_length = ret.field_length.max(ret.decimal_count + 2);
+    }
+    ret
+}
+
+/// Converts a value read from an input file into the type of the output field.
+fn convert_field_data(value: FieldData, field: &AttributeField) -> FieldData {
+    match value {
+        FieldData::Null => FieldData::Null,
+        FieldData::Text(_) => value,
+        FieldData::Int(v) => {
+            if field.field_type == 'C' {
+                FieldData::Text(v.to_string())
+            } else if field.decimal_count > 0 {
+                FieldData::Real(v as f64)
+            } else {
+                value
+            }
+        }
+        _ => {
+            if field.field_type == 'C' {
+                FieldData::Text(value.to_string())
+            } else {
+                value
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{convert_field_data, promote_field, promote_record, promote_shape_type};
+    use whitebox_common::structures::Point2D;
+    use whitebox_vector::*;
+
+    #[test]
+    fn test_promote_field() {
+        let int = AttributeField::new("VALUE", FieldDataType::Int, 6u8, 0u8);
+        let real = AttributeField::new("value", FieldDataType::Real, 10u8, 3u8);
+        let text = AttributeField::new("Value", FieldDataType::Text, 8u8, 0u8);
+
+        // integer and floating-point fields are combined into a floating-point field
+        let field = promote_field(&int, &real);
+        assert_eq!(field.field_type, 'F');
+        assert_eq!(field.field_length, 10u8);
+        assert_eq!(field.decimal_count, 3u8);
+        assert_eq!(field.name, "VALUE");
+
+        // any other combination becomes text, wide enough to hold the numbers
+        let field = promote_field(&real, &text);
+        assert_eq!(field.field_type, 'C');
+        assert_eq!(field.decimal_count, 0u8);
+        assert_eq!(field.field_length, 20u8);
+
+        // like fields keep their type, widened to the longer of the two
+        let field = promote_field(&int, &AttributeField::new("VALUE", FieldDataType::Int, 9u8, 0u8));
+        assert_eq!(field.field_type, 'N');
+        assert_eq!(field.field_length, 9u8);
+    }
+
+    #[test]
+    fn test_convert_field_data() {
+        let real = promote_field(
+            &AttributeField::new("VALUE", FieldDataType::Int, 6u8, 0u8),
+            &AttributeField::new("VALUE", FieldDataType::Real, 10u8, 3u8),
+        );
+        assert_eq!(convert_field_data(FieldData::Int(5), &real), FieldData::Real(5.0));
+        assert_eq!(convert_field_data(FieldData::Real(2.5), &real), FieldData::Real(2.5));
+        assert_eq!(convert_field_data(FieldData::Null, &real), FieldData::Null);
+
+        let text = promote_field(&real, &AttributeField::new("VALUE", FieldDataType::Text, 8u8, 0u8));
+        assert_eq!(convert_field_data(FieldData::Int(5), &text), FieldData::Text("5".to_string()));
+        assert_eq!(convert_field_data(FieldData::Real(2.5), &text), FieldData::Text("2.5".to_string()));
+        assert_eq!(convert_field_data(FieldData::Bool(true), &text), FieldData::Text(FieldData::Bool(true).to_string()));
+        assert_eq!(convert_field_data(FieldData::Text("a".to_string()), &text), FieldData::Text("a".to_string()));
+
+        let int = AttributeField::new("VALUE", FieldDataType::Int, 6u8, 0u8);
+        assert_eq!(convert_field_data(FieldData::Int(5), &int), FieldData::Int(5));
+    }
+
+    #[test]
+    fn test_promote_shape_type() {
+        assert_eq!(promote_shape_type(ShapeType::PolyLine, ShapeType::PolyLineZ), ShapeType::PolyLineZ);
+        assert_eq!(promote_shape_type(ShapeType::PolyLineZ, ShapeType::PolyLineM), ShapeType::PolyLineZ);
+        assert_eq!(promote_shape_type(ShapeType::PolyLineM, ShapeType::PolyLine), ShapeType::PolyLineM);
+        assert_eq!(promote_shape_type(ShapeType::Point, ShapeType::Point), ShapeType::Point);
+
+        let mut line = ShapefileGeometry::new(ShapeType::PolyLine);
+        line.add_part(&[Point2D::new(0.0, 0.0), Point2D::new(1.0, 1.0)]);
+        let z = promote_record(line.clone(), ShapeType::PolyLineZ);
+        assert_eq!(z.shape_type, ShapeType::PolyLineZ);
+        assert_eq!(z.z_array, vec![0.0, 0.0]);
+        assert!(!z.has_m_data());
+        let m = promote_record(line, ShapeType::PolyLineM);
+        assert_eq!(m.shape_type, ShapeType::PolyLineM);
+        assert_eq!(m.m_array, vec![0.0, 0.0]);
+
+        let mut point = ShapefileGeometry::new(ShapeType::Point);
+        point.add_point(Point2D::new(1.0, 2.0));
+        let z = promote_record(point, ShapeType::PointZ);
+        assert_eq!((z.z_array.len(), z.m_array.len()), (1, 1));
+    }
+}
@@ -1,53 +1,86 @@
 // private sub-module defined in other files
 mod add_point_coordinates_to_table;
 mod clean_vector;
+mod compare_rasters;
+mod compare_vectors;
 mod convert_nodata_to_zero;
 mod convert_raster_format;
 mod csv_points_to_vector;
+mod export_stac_items;
 mod export_table_to_csv;
+mod hex_to_square_grid;
 mod join_tables;
+mod lines_to_points;
 mod lines_to_polygons;
 mod merge_table_with_csv;
 mod merge_vectors;
 mod modify_nodata_value;
 mod multipart_to_singlepart;
 mod new_raster;
+mod pipeline;
+mod points_to_lines;
 mod polygons_to_lines;
 mod print_geotiff_tags;
+mod raster_summary;
 mod raster_to_vector_lines;
 mod raster_to_vector_points;
 mod raster_to_vector_polygons;
+mod raster_to_xyz;
 mod reinitialize_attribute_table;
 mod remove_polygon_holes;
+mod reproject_raster;
 mod set_nodata_value;
 mod singlepart_to_multipart;
+mod split_vector_layer;
+mod square_to_hex_grid;
+mod stac;
+mod stac_search;
+mod translate_nodata;
 mod vector_lines_to_raster;
 mod vector_points_to_raster;
 mod vector_polygons_to_raster;
+mod xyz_to_raster;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::add_point_coordinates_to_table::AddPointCoordinatesToTable;
 pub use self::clean_vector::CleanVector;
+pub use self::compare_rasters::CompareRasters;
+pub use self::compare_vectors::CompareVectors;
 pub use self::convert_nodata_to_zero::ConvertNodataToZero;
 pub use self::convert_raster_format::ConvertRasterFormat;
 pub use self::csv_points_to_vector::CsvPointsToVector;
+pub use self::export_stac_items::ExportStacItems;
 pub use self::export_table_to_csv::ExportTableToCsv;
+pub use self::hex_to_square_grid::HexToSquareGrid;
 pub use self::join_tables::JoinTables;
+pub use self::lines_to_points::LinesToPoints;
 pub use self::lines_to_polygons::LinesToPolygons;
 pub use self::merge_table_with_csv::MergeTableWithCsv;
 pub use self::merge_vectors::MergeVectors;
 pub use self::modify_nodata_value::ModifyNoDataValue;
 pub use self::multipart_to_singlepart::MultiPartToSinglePart;
 pub use self::new_raster::NewRasterFromBase;
+pub use self::pipeline::Pipeline;
+pub use self::points_to_lines::PointsToLines;
 pub use self::polygons_to_lines::PolygonsToLines;
 pub use self::print_geotiff_tags::PrintGeoTiffTags;
+pub use self::raster_summary::RasterSummary;
 pub use self::raster_to_vector_lines::RasterToVectorLines;
 pub use self::raster_to_vector_points::RasterToVectorPoints;
 pub use self::raster_to_vector_polygons::RasterToVectorPolygons;
+pub use self::raster_to_xyz::RasterToXyz;
 pub use self::reinitialize_attribute_table::ReinitializeAttributeTable;
 pub use self::remove_polygon_holes::RemovePolygonHoles;
+pub use self::reproject_raster::ReprojectRaster;
 pub use self::set_nodata_value::SetNodataValue;
 pub use self::singlepart_to_multipart::SinglePartToMultiPart;
+pub use self::split_vector_layer::SplitVectorLayer;
+pub use self::square_to_hex_grid::SquareToHexGrid;
+pub use self::stac_search::StacSearch;
+pub use self::translate_nodata::TranslateNodata;
 pub use self::vector_lines_to_raster::VectorLinesToRaster;
 pub use self::vector_points_to_raster::VectorPointsToRaster;
 pub use self::vector_polygons_to_raster::VectorPolygonsToRaster;
+pub use self::xyz_to_raster::XyzToRaster;
+
+pub(crate) use self::raster_to_vector_polygons::trace_clump_polygons;
@@ -10,7 +10,7 @@ use whitebox_raster::*;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to modify the value of pixels containing the NoData value for an input raster image.
@@ -116,40 +116,11 @@ impl WhiteboxTool for ModifyNoDataValue {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut new_nodata_value = -32768f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-new_value" {
-                new_nodata_value = if keyval {
-                    vec[1].to_string().parse().unwrap()
-                } else {
-                    args[i + 1].parse().unwrap()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let new_nodata_value = parsed.get_f64("new_value").unwrap_or(-32768f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -162,12 +133,7 @@ impl WhiteboxTool for ModifyNoDataValue {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            // Windows now takes forward slash in directory names.
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
         let mut input = Raster::new(&input_file, "rw")?;
 
@@ -183,14 +149,15 @@ impl WhiteboxTool for ModifyNoDataValue {
         for row in 0..rows {
             for col in 0..columns {
                 value = input.get_value(row, col);
-                if value == nodata {
+                if is_nodata(value, nodata) {
                     input.set_value(row, col, new_nodata_value);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
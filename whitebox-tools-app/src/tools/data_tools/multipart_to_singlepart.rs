@@ -65,10 +65,10 @@ impl MultiPartToSinglePart {
 
         parameters.push(ToolParameter {
             name: "Exclude hole parts?".to_owned(),
-            flags: vec!["--exclude_holes".to_owned()],
+            flags: vec!["--exc".to_owned(), "--hole".to_owned(), "--exclude_holes".to_owned()],
             description: "Exclude hole parts from the feature splitting? (holes will continue to belong to their features in output.)".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some(String::from("true")),
+            default_value: Some(String::from("false")),
             optional: true,
         });
 
@@ -141,48 +141,15 @@ impl WhiteboxTool for MultiPartToSinglePart {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut exclude_holes = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let exclude_holes = parsed.get_bool("exclude_holes");
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val.contains("-exc") || flag_val.contains("hole") {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    exclude_holes = true;
-                }
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -199,13 +166,9 @@ impl WhiteboxTool for MultiPartToSinglePart {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Shapefile::read(&input_file)?;
 
@@ -257,11 +220,12 @@ impl WhiteboxTool for MultiPartToSinglePart {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -309,11 +273,12 @@ impl WhiteboxTool for MultiPartToSinglePart {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -395,11 +360,12 @@ impl WhiteboxTool for MultiPartToSinglePart {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -42,7 +42,7 @@ impl NewRasterFromBase {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input Base File".to_owned(),
-            flags: vec!["-i".to_owned(), "--base".to_owned()],
+            flags: vec!["-i".to_owned(), "--input".to_owned(), "--base".to_owned()],
             description: "Input base raster file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::RasterAndVector(
                 VectorGeometryType::Any,
@@ -72,7 +72,7 @@ impl NewRasterFromBase {
 
         parameters.push(ToolParameter{
             name: "Data Type".to_owned(), 
-            flags: vec!["--data_type".to_owned()], 
+            flags: vec!["--datatype".to_owned(), "--data_type".to_owned()], 
             description: "Output raster data type; options include 'double' (64-bit), 'float' (32-bit), and 'integer' (signed 16-bit) (default is 'float').".to_owned(),
             parameter_type: ParameterType::OptionList(vec!["double".to_owned(), "float".to_owned(), "integer".to_owned()]),
             default_value: Some("float".to_owned()),
@@ -147,67 +147,14 @@ impl WhiteboxTool for NewRasterFromBase {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        _reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut base_file = String::new();
-        let mut output_file = String::new();
-        let mut out_val_str = String::new();
-        let mut data_type = String::new();
-        let mut cell_size = 0f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-base" || flag_val == "-i" || flag_val == "-input" {
-                base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-value" {
-                out_val_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-data_type" || flag_val == "-datatype" {
-                data_type = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-cell_size" {
-                cell_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let out_val_str = parsed.get_string("value").unwrap_or_default();
+        let data_type = parsed.get_string("data_type").unwrap_or_default();
+        let cell_size = parsed.get_f64("cell_size").unwrap_or(0f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -220,14 +167,8 @@ impl WhiteboxTool for NewRasterFromBase {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !base_file.contains(&sep) && !base_file.contains("/") {
-            base_file = format!("{}{}", working_directory, base_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        base_file = resolve_input_path(&base_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let start = Instant::now();
 
@@ -273,7 +214,7 @@ impl WhiteboxTool for NewRasterFromBase {
             configs.resolution_y = cell_size;
             configs.nodata = nodata;
             configs.photometric_interp = PhotometricInterpretation::Continuous;
-            configs.projection = input.projection.clone();
+            configs.set_crs(&whitebox_common::spatial_ref_system::CoordinateReferenceSystem::from_wkt(&input.projection));
 
             Raster::initialize_using_config(&output_file, &configs)
         } else {
@@ -282,12 +223,11 @@ impl WhiteboxTool for NewRasterFromBase {
             Raster::initialize_using_file(&output_file, &base)
         };
 
-        if output.configs.nodata != nodata || out_val != nodata {
+        if output.configs.nodata != nodata || !is_nodata(out_val, nodata) {
             output.configs.nodata = nodata;
             output.reinitialize_values(out_val);
         }
 
-
         if data_type.to_lowercase().contains("i") {
             output.configs.data_type = DataType::I16;
         } else if data_type.to_lowercase().contains("d") {
@@ -0,0 +1,558 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+use whitebox_common::utils::is_memory_dataset;
+use whitebox_raster::{memory_raster_exists, memory_raster_key, remove_memory_raster};
+
+/// This tool runs a workflow of several tools, described in a JSON or YAML file (`--input`), as a
+/// single process. The intermediate raster outputs of the workflow may be declared as in-memory datasets, by
+/// giving them names that begin with `memory:`, in which case they are passed between the steps of
+/// the workflow without being written to, and read back from, GeoTIFF files. For multi-step workflows
+/// on large rasters, such as the conditioning of a DEM, the calculation of flow directions and flow
+/// accumulation, and the extraction of streams, this avoids a considerable amount of encoding,
+/// compression and disk I/O, and keeps the intermediate data at full precision.
+///
+/// The workflow file contains a list of `steps`, each of which names a `tool` and its arguments
+/// (`args`), given either as an object of parameter names and values, or as a list of command-line
+/// arguments. An optional step `id` (by default, 'step1', 'step2', etc.) may be used to refer to the
+/// step in the `depends_on` lists of other steps, and an optional `working_directory` overrides that
+/// of the tool run:
+///
+/// ```json
+/// {
+///   "steps": [
+///     { "id": "breach", "tool": "BreachDepressionsLeastCost",
+///       "args": { "dem": "dem.tif", "output": "memory:breached", "dist": 100, "fill": true } },
+///     { "id": "pointer", "tool": "D8Pointer",
+///       "args": { "dem": "memory:breached", "output": "memory:pointer" } },
+///     { "id": "accumulation", "tool": "D8FlowAccumulation",
+///       "args": { "input": "memory:pointer", "output": "memory:accum", "pntr": true, "out_type": "cells" } },
+///     { "id": "streams", "tool": "ExtractStreams",
+///       "args": { "flow_accum": "memory:accum", "output": "streams.tif", "threshold": 1000 } }
+///   ]
+/// }
+/// ```
+///
+/// Files with a *.yaml* or *.yml* extension are read as YAML, and all others as JSON. The same
+/// workflow, in YAML:
+///
+/// ```yaml
+/// steps:
+///   - id: breach
+///     tool: BreachDepressionsLeastCost
+///     args: { dem: dem.tif, output: "memory:breached", dist: 100, fill: true }
+///   - id: pointer
+///     tool: D8Pointer
+///     args: { dem: "memory:breached", output: "memory:pointer" }
+///   - id: accumulation
+///     tool: D8FlowAccumulation
+///     args: { input: "memory:pointer", output: "memory:accum", pntr: true, out_type: cells }
+///   - id: streams
+///     tool: ExtractStreams
+///     args: { flow_accum: "memory:accum", output: streams.tif, threshold: 1000 }
+/// ```
+///
+/// Boolean parameters are set with `true` and omitted with `false`, and lists of values, e.g. of input
+/// files, are joined with semicolons. The steps form a directed acyclic graph, in which a step depends
+/// on those that produce its input datasets, whether in-memory or files, and on those listed in its
+/// `depends_on`. The steps are run in an order that respects these dependencies, and otherwise in the
+/// order in which they are listed. The workflow is checked before any step is run, and is rejected if
+/// it names an unknown tool or step, contains a cycle, has two steps that write the same output,
+/// writes a vector output to memory, or reads an in-memory dataset that no step produces. Each in-memory dataset is removed once the last
+/// step that reads it has finished, and so a final output should be given a file name. The
+/// `--dry_run` flag prints the order in which the steps would be run without running them.
+///
+/// Only rasters may be held in memory; vector and LiDAR datasets are always read from and written to
+/// files. If a step fails, the remaining steps are not run.
+///
+/// Other keys of a step are ignored, and so the `position` of each step saved by the pipeline builder
+/// of the WhiteboxTools Runner (*wb_runner.py*), in which workflows may be assembled by connecting the
+/// outputs of tools to the inputs of others, does not affect the run.
+///
+/// # See Also
+/// `RasterSummary`
+pub struct Pipeline {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl Pipeline {
+    pub fn new() -> Pipeline {
+        // public constructor
+        let name = "Pipeline".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Runs a workflow of tools described in a JSON or YAML file, passing intermediate rasters between steps in memory."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Workflow File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input JSON or YAML (*.yaml, *.yml) workflow file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Dry run?".to_owned(),
+            flags: vec!["--dry_run".to_owned()],
+            description: "Print the order of the steps without running them.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=hydro_workflow.json
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=hydro_workflow.yaml --dry_run",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        Pipeline {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for Pipeline {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let dry_run = parsed.get_bool("dry_run");
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        let contents = fs::read_to_string(&input_file)?;
+        let workflow = parse_workflow(&contents, &input_file)?;
+
+        let mut wd = working_directory.to_string();
+        if let Some(v) = workflow.get("working_directory").and_then(|v| v.as_str()) {
+            wd = v.to_string();
+            if !wd.ends_with(path::MAIN_SEPARATOR) && !wd.ends_with('/') {
+                wd.push(path::MAIN_SEPARATOR);
+            }
+        }
+        let tm = ToolManager::new(&wd, &verbose)?;
+
+        let steps = parse_steps(&workflow, &tm, &wd)?;
+        let order = order_steps(&steps)?;
+
+        // The position in the run order of the last step to read each in-memory dataset.
+        let mut last_use: HashMap<String, usize> = HashMap::new();
+        for (position, &s) in order.iter().enumerate() {
+            for dataset in steps[s].inputs.iter().filter(|d| is_memory_dataset(d)) {
+                last_use.insert(dataset.clone(), position);
+            }
+        }
+        for step in &steps {
+            for dataset in step.outputs.iter().filter(|d| is_memory_dataset(d)) {
+                if !last_use.contains_key(dataset) {
+                    println!(
+                        "Warning: the in-memory dataset {} written by step '{}' is not read by any other step and will be discarded.",
+                        dataset, step.id
+                    );
+                }
+            }
+        }
+
+        if dry_run || verbose {
+            println!("Run order:");
+            for (position, &s) in order.iter().enumerate() {
+                println!("{}. {} ({}) {}", position + 1, steps[s].id, steps[s].tool, steps[s].args.join(" "));
+            }
+        }
+        if dry_run {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        for (position, &s) in order.iter().enumerate() {
+            if let Err(e) = reporter.check_cancelled() {
+                remove_memory_outputs(&steps);
+                return Err(e);
+            }
+            let step = &steps[s];
+            if verbose {
+                println!("\nStep {} of {}: {} ({})", position + 1, order.len(), step.id, step.tool);
+            }
+            if let Err(e) = tm.run_tool_with_progress(step.tool.clone(), step.args.clone(), reporter) {
+                remove_memory_outputs(&steps);
+                return Err(Error::new(
+                    e.kind(),
+                    format!("Step '{}' ({}) failed: {}", step.id, step.tool, e),
+                ));
+            }
+            for dataset in step.outputs.iter().filter(|d| is_memory_dataset(d)) {
+                if !memory_raster_exists(dataset) {
+                    remove_memory_outputs(&steps);
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "Step '{}' ({}) did not create the in-memory dataset {}; only raster outputs may be held in memory.",
+                            step.id, step.tool, dataset
+                        ),
+                    ));
+                }
+            }
+            // Free the in-memory datasets that no later step reads.
+            for (dataset, _) in last_use.iter().filter(|(_, &p)| p == position) {
+                remove_memory_raster(dataset);
+            }
+        }
+        remove_memory_outputs(&steps);
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!(
+                "\nCompleted {} steps. Elapsed Time: {}",
+                order.len(),
+                elapsed_time
+            );
+        }
+
+        Ok(())
+    }
+}
+
+struct Step {
+    id: String,
+    tool: String,
+    args: Vec<String>,
+    depends_on: Vec<String>,
+    // The datasets that the step reads and writes, as in-memory dataset keys or resolved file names.
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+}
+
+/// Reads and checks the steps of a workflow.
+fn parse_steps(workflow: &Value, tm: &ToolManager, working_directory: &str) -> Result<Vec<Step>, Error> {
+    let list = match workflow.get("steps").and_then(|v| v.as_array()) {
+        Some(list) if !list.is_empty() => list,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The workflow must contain a non-empty list of 'steps'.",
+            ))
+        }
+    };
+    let mut steps: Vec<Step> = vec![];
+    for (i, v) in list.iter().enumerate() {
+        let id = match v.get("id") {
+            Some(id) => id
+                .as_str()
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("The id of step {} must be a string.", i + 1)))?
+                .to_string(),
+            None => format!("step{}", i + 1),
+        };
+        if steps.iter().any(|s| s.id == id) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("There is more than one step with the id '{}'.", id),
+            ));
+        }
+        let tool_name = v.get("tool").and_then(|t| t.as_str()).unwrap_or("").trim().to_string();
+        let tool = match tm.get_tool(&tool_name) {
+            Some(t) => t,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Step '{}' names an unrecognized tool '{}'.", id, tool_name),
+                ))
+            }
+        };
+        let args = step_args(v.get("args"), &id)?;
+        let depends_on = match v.get("depends_on") {
+            Some(Value::Array(a)) => a.iter().filter_map(|d| d.as_str()).map(|d| d.to_string()).collect(),
+            Some(Value::String(d)) => vec![d.to_string()],
+            _ => vec![],
+        };
+
+        let mut inputs = vec![];
+        let mut outputs = vec![];
+        for (param, value) in parse_tool_args(&tool.get_tool_parameters(), &args)? {
+            let parameter_type = &param["parameter_type"];
+            if parameter_type.get("NewFile").is_some() {
+                if is_memory_dataset(&value) && parameter_type["NewFile"] != "Raster" {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Step '{}' writes {} to memory, but only raster outputs may be held in memory.", id, value),
+                    ));
+                }
+                outputs.push(dataset_name(&value, working_directory));
+            } else if parameter_type.get("FileList").is_some() {
+                let delimiter = if value.contains(';') { ';' } else { ',' };
+                for file in value.split(delimiter).filter(|f| !f.trim().is_empty()) {
+                    inputs.push(dataset_name(file, working_directory));
+                }
+            } else if parameter_type.get("ExistingFile").is_some()
+                || (parameter_type.get("ExistingFileOrFloat").is_some() && value.trim().parse::<f64>().is_err())
+            {
+                inputs.push(dataset_name(&value, working_directory));
+            }
+        }
+        inputs.retain(|d| !d.is_empty());
+        outputs.retain(|d| !d.is_empty());
+
+        steps.push(Step {
+            id,
+            tool: tool.get_tool_name(),
+            args,
+            depends_on,
+            inputs,
+            outputs,
+        });
+    }
+
+    for step in &steps {
+        for d in &step.depends_on {
+            if !steps.iter().any(|s| &s.id == d) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Step '{}' depends on an unknown step '{}'.", step.id, d),
+                ));
+            }
+        }
+        for output in &step.outputs {
+            if let Some(other) = steps.iter().find(|s| s.id != step.id && s.outputs.contains(output)) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Steps '{}' and '{}' both write {}.", step.id, other.id, output),
+                ));
+            }
+        }
+        for input in step.inputs.iter().filter(|d| is_memory_dataset(d)) {
+            if !steps.iter().any(|s| s.outputs.contains(input)) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Step '{}' reads the in-memory dataset {}, which no step writes.", step.id, input),
+                ));
+            }
+        }
+    }
+    Ok(steps)
+}
+
+/// Parses a workflow file, as YAML if it has a *.yaml* or *.yml* extension and otherwise as JSON.
+fn parse_workflow(contents: &str, file_name: &str) -> Result<Value, Error> {
+    let extension = path::Path::new(file_name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let (workflow, format) = if extension == "yaml" || extension == "yml" {
+        (serde_yaml::from_str::<Value>(contents).map_err(|e| e.to_string()), "YAML")
+    } else {
+        (serde_json::from_str::<Value>(contents).map_err(|e| e.to_string()), "JSON")
+    };
+    workflow.map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("The workflow file {} is not valid {}: {}", file_name, format, e),
+        )
+    })
+}
+
+/// Converts the arguments of a step to command-line arguments.
+fn step_args(args: Option<&Value>, id: &str) -> Result<Vec<String>, Error> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("The arguments of step '{}' must be an object of parameter values or a list of strings.", id),
+        )
+    };
+    let mut ret = vec![];
+    match args {
+        None => {}
+        Some(Value::Array(list)) => {
+            for a in list {
+                ret.push(a.as_str().ok_or_else(invalid)?.to_string());
+            }
+        }
+        Some(Value::Object(map)) => {
+            for (key, value) in map {
+                let flag = if key.starts_with('-') {
+                    key.clone()
+                } else {
+                    format!("--{}", key)
+                };
+                match value {
+                    Value::Bool(true) => ret.push(flag),
+                    Value::Bool(false) | Value::Null => {}
+                    Value::String(s) => ret.push(format!("{}={}", flag, s)),
+                    Value::Number(n) => ret.push(format!("{}={}", flag, n)),
+                    Value::Array(list) => {
+                        let mut items = vec![];
+                        for item in list {
+                            match item {
+                                Value::String(s) => items.push(s.clone()),
+                                Value::Number(n) => items.push(n.to_string()),
+                                _ => return Err(invalid()),
+                            }
+                        }
+                        ret.push(format!("{}={}", flag, items.join(";")));
+                    }
+                    Value::Object(_) => return Err(invalid()),
+                }
+            }
+        }
+        Some(_) => return Err(invalid()),
+    }
+    Ok(ret)
+}
+
+/// Returns the name by which a dataset is identified in the workflow graph.
+fn dataset_name(file_name: &str, working_directory: &str) -> String {
+    let file_name = file_name.trim();
+    if is_memory_dataset(file_name) {
+        return format!("memory:{}", memory_raster_key(file_name));
+    }
+    resolve_path(file_name, working_directory)
+}
+
+/// Returns the indices of the steps in the order in which they are to be run, i.e. each step
+/// after the steps that it depends on and otherwise in the order in which they are listed.
+fn order_steps(steps: &[Step]) -> Result<Vec<usize>, Error> {
+    let n = steps.len();
+    let mut predecessors: Vec<Vec<usize>> = vec![vec![]; n];
+    for (i, step) in steps.iter().enumerate() {
+        for (j, other) in steps.iter().enumerate() {
+            if i != j
+                && (step.depends_on.contains(&other.id)
+                    || step.inputs.iter().any(|d| other.outputs.contains(d)))
+            {
+                predecessors[i].push(j);
+            }
+        }
+    }
+    let mut done = vec![false; n];
+    let mut order = vec![];
+    while order.len() < n {
+        match (0..n).find(|&i| !done[i] && predecessors[i].iter().all(|&j| done[j])) {
+            Some(i) => {
+                done[i] = true;
+                order.push(i);
+            }
+            None => {
+                let remaining = (0..n)
+                    .filter(|&i| !done[i])
+                    .map(|i| format!("'{}'", steps[i].id))
+                    .collect::<Vec<String>>();
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("The workflow contains a cycle among the steps {}.", remaining.join(", ")),
+                ));
+            }
+        }
+    }
+    Ok(order)
+}
+
+/// Removes any in-memory datasets written by the steps of the workflow.
+fn remove_memory_outputs(steps: &[Step]) {
+    for step in steps {
+        for dataset in step.outputs.iter().filter(|d| is_memory_dataset(d)) {
+            remove_memory_raster(dataset);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_workflow, step_args};
+
+    #[test]
+    fn test_parses_yaml_workflows() {
+        let json = r#"{ "steps": [ { "tool": "D8Pointer", "args": { "dem": "memory:dem", "output": "pntr.tif", "esri_pntr": true, "n": 2 } } ] }"#;
+        let yaml = "steps:\n  - tool: D8Pointer\n    args: { dem: \"memory:dem\", output: pntr.tif, esri_pntr: true, n: 2 }\n";
+        let from_json = parse_workflow(json, "workflow.json").unwrap();
+        let from_yaml = parse_workflow(yaml, "workflow.YML").unwrap();
+        assert_eq!(from_json, from_yaml);
+        assert_eq!(
+            step_args(from_yaml["steps"][0].get("args"), "step1").unwrap(),
+            vec!["--dem=memory:dem", "--esri_pntr", "--n=2", "--output=pntr.tif"]
+        );
+        // the format follows the extension, not the contents
+        assert!(parse_workflow(yaml, "workflow.json").is_err());
+        assert!(parse_workflow("steps: [", "workflow.yaml")
+            .unwrap_err()
+            .to_string()
+            .contains("not valid YAML"));
+    }
+}
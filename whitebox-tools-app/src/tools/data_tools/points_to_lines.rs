@@ -0,0 +1,434 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::structures::Point2D;
+use crate::tools::*;
+use whitebox_vector::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool can be used to build vector polylines from sequences of vector points, e.g. to
+/// convert the fixes of one or more GPS tracks into track lines. Points may optionally be grouped
+/// into separate lines using a grouping field (`--group_field`), such that each unique value of
+/// this field in the input attribute table results in a separate output line. The points within
+/// each line are connected in the order given by the optional `--order_field` (e.g. a time stamp or
+/// sequence number), or in the order that they appear within the input file when no ordering field
+/// is specified. Numeric ordering fields are sorted numerically and all other field types are sorted
+/// alphabetically.
+///
+/// The optional `--max_gap` parameter can be used to break a line wherever the distance between
+/// consecutive points exceeds a threshold value, which is useful when tracks contain recording gaps.
+/// Lines containing fewer than two points are not output. If the input points contain z-values
+/// and/or measures, these are carried over to the output line vertices. Alternatively, when the
+/// `--measure` flag is specified, the output line vertices are assigned measures equal to the
+/// distance along the line, making the output suitable for linear referencing.
+///
+/// The output attribute table contains the FID, the group field value (if specified), the number
+/// of points in the line (NUM_PTS) and the line length (LENGTH).
+///
+/// # See Also
+/// `LinesToPoints`, `ExtractNodes`
+pub struct PointsToLines {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl PointsToLines {
+    pub fn new() -> PointsToLines {
+        // public constructor
+        let name = "PointsToLines".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Builds vector polylines from ordered sequences of vector points.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Points File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector points file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Lines File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polyline file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Grouping Field".to_owned(),
+            flags: vec!["--group_field".to_owned()],
+            description: "Field used to group points into separate lines (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Ordering Field".to_owned(),
+            flags: vec!["--order_field".to_owned()],
+            description: "Field used to order the points within lines (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Gap Distance".to_owned(),
+            flags: vec!["--max_gap".to_owned()],
+            description: "Distance between consecutive points above which a line is broken (optional).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output distance-along measures?".to_owned(),
+            flags: vec!["--measure".to_owned()],
+            description: "Assign the distance along the line as the measure of each output vertex.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=gps_fixes.shp -o=tracks.shp --group_field=TRACK_ID --order_field=TIME --max_gap=500.0 --measure",
+            short_exe, name
+        ).replace("*", &sep);
+
+        PointsToLines {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for PointsToLines {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let group_field = parsed.get_string("group_field").unwrap_or_default();
+        let order_field = parsed.get_string("order_field").unwrap_or_default();
+        let mut max_gap = f64::INFINITY;
+        let output_measures = parsed.get_bool("measure");
+
+        // read the arguments
+        if let Some(value) = parsed.get_f64("max_gap") {
+            max_gap = value;
+            if max_gap <= 0f64 {
+                max_gap = f64::INFINITY;
+            }
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        // make sure the input vector file is of point or multipoint type
+        let base_type = input.header.shape_type.base_shape_type();
+        if base_type != ShapeType::Point && base_type != ShapeType::MultiPoint {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of a POINT or MULTIPOINT base shape type.",
+            ));
+        }
+
+        let use_group = !group_field.is_empty();
+        if use_group && input.attributes.get_field_num(&group_field).is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The grouping field '{}' does not exist in the input attribute table.", group_field),
+            ));
+        }
+        let use_order = !order_field.is_empty();
+        if use_order && input.attributes.get_field_num(&order_field).is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The ordering field '{}' does not exist in the input attribute table.", order_field),
+            ));
+        }
+
+        let dimension = input.header.shape_type.dimension();
+        let output_type = if dimension == ShapeTypeDimension::Z {
+            ShapeType::PolyLineZ
+        } else if dimension == ShapeTypeDimension::Measure || output_measures {
+            ShapeType::PolyLineM
+        } else {
+            ShapeType::PolyLine
+        };
+
+        // Read the points, grouping them in order of first appearance.
+        let mut points: Vec<SequencePoint> = vec![];
+        let mut group_values: Vec<FieldData> = vec![];
+        let mut group_lookup: HashMap<String, usize> = HashMap::new();
+        for record_num in 0..input.num_records {
+            reporter.check_cancelled()?;
+            let record = input.get_record(record_num);
+            if record.shape_type == ShapeType::Null {
+                continue;
+            }
+            let group = if use_group {
+                let value = input.attributes.get_value(record_num, &group_field);
+                let key = format!("{}", value);
+                match group_lookup.get(&key) {
+                    Some(&g) => g,
+                    None => {
+                        group_lookup.insert(key, group_values.len());
+                        group_values.push(value);
+                        group_values.len() - 1
+                    }
+                }
+            } else {
+                if group_values.is_empty() {
+                    group_values.push(FieldData::Null);
+                }
+                0
+            };
+            let order = if use_order {
+                input.attributes.get_value(record_num, &order_field)
+            } else {
+                FieldData::Null
+            };
+            for i in 0..record.points.len() {
+                points.push(SequencePoint {
+                    group: group,
+                    order: order.clone(),
+                    index: points.len(),
+                    point: record.points[i],
+                    z: if record.has_z_data() { record.z_array[i] } else { 0f64 },
+                    m: if record.has_m_data() { record.m_array[i] } else { 0f64 },
+                });
+            }
+        }
+
+        // Sort the points by group, then order field, then input order.
+        points.sort_by(|a, b| {
+            a.group
+                .cmp(&b.group)
+                .then_with(|| compare_field_data(&a.order, &b.order))
+                .then_with(|| a.index.cmp(&b.index))
+        });
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, output_type, false)?;
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        if use_group {
+            let i = input.attributes.get_field_num(&group_field).unwrap();
+            output.attributes.add_field(input.attributes.get_field(i));
+        }
+        output
+            .attributes
+            .add_field(&AttributeField::new("NUM_PTS", FieldDataType::Int, 7u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("LENGTH", FieldDataType::Real, 12u8, 4u8));
+
+        let mut fid = 1i32;
+        let num_points = points.len();
+        let mut line_start = 0usize;
+        for i in 0..num_points {
+            let is_line_end = i == num_points - 1
+                || points[i + 1].group != points[i].group
+                || points[i + 1].point.distance(&points[i].point) > max_gap;
+            if is_line_end {
+                let line = &points[line_start..=i];
+                if line.len() > 1 {
+                    let mut vertices: Vec<Point2D> = Vec::with_capacity(line.len());
+                    let mut measures: Vec<f64> = Vec::with_capacity(line.len());
+                    let mut z_values: Vec<f64> = Vec::with_capacity(line.len());
+                    let mut length = 0f64;
+                    for j in 0..line.len() {
+                        if j > 0 {
+                            length += line[j].point.distance(&line[j - 1].point);
+                        }
+                        vertices.push(line[j].point);
+                        measures.push(if output_measures { length } else { line[j].m });
+                        z_values.push(line[j].z);
+                    }
+                    let mut sfg = ShapefileGeometry::new(output_type);
+                    match output_type {
+                        ShapeType::PolyLineZ => sfg.add_partz(&vertices, &measures, &z_values),
+                        ShapeType::PolyLineM => sfg.add_partm(&vertices, &measures),
+                        _ => sfg.add_part(&vertices),
+                    }
+                    output.add_record(sfg);
+
+                    let mut atts = vec![FieldData::Int(fid)];
+                    if use_group {
+                        atts.push(group_values[line[0].group].clone());
+                    }
+                    atts.push(FieldData::Int(line.len() as i32));
+                    atts.push(FieldData::Real(length));
+                    output.attributes.add_record(atts, false);
+                    fid += 1;
+                }
+                line_start = i + 1;
+            }
+
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * (i + 1) as f64 / num_points as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if output.num_records == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No lines containing two or more points could be created from the input points.",
+            ));
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+struct SequencePoint {
+    group: usize,
+    order: FieldData,
+    index: usize,
+    point: Point2D,
+    z: f64,
+    m: f64,
+}
+
+/// Compares two attribute values, numerically if both are numeric and alphabetically otherwise.
+fn compare_field_data(a: &FieldData, b: &FieldData) -> Ordering {
+    let as_number = |v: &FieldData| match v {
+        FieldData::Int(i) => Some(*i as f64),
+        FieldData::Real(f) => Some(*f),
+        _ => None,
+    };
+    match (as_number(a), as_number(b)) {
+        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+        _ => format!("{}", a).cmp(&format!("{}", b)),
+    }
+}
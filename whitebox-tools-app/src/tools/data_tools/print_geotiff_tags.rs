@@ -117,40 +117,12 @@ impl WhiteboxTool for PrintGeoTiffTags {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        _reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
         // let mut output_file = String::new();
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-                // } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                //     if keyval {
-                //         output_file = vec[1].to_string();
-                //     } else {
-                //         output_file = args[i + 1].to_string();
-                //     }
-            }
-        }
-
         if verbose {
             let tool_name = self.get_tool_name();
             let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28); 
@@ -162,11 +134,7 @@ impl WhiteboxTool for PrintGeoTiffTags {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_path(&input_file, working_directory);
 
         // make sure that it is a tiff file
         if !input_file.to_lowercase().ends_with(".tiff")
@@ -0,0 +1,272 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::geotiff::read_tiff_layout;
+use whitebox_raster::*;
+use crate::tools::*;
+use serde_json::json;
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool reports a comprehensive summary of a raster (`--input`) in JSON form, analogous to
+/// GDAL's `gdalinfo` utility. The summary includes the file format and size, the grid dimensions,
+/// resolution and extent, the number of bands, data type, photometric interpretation, nodata value,
+/// units, the coordinate reference system (EPSG code and WKT), the display range, the metadata
+/// entries, and the distribution statistics (minimum, maximum, mean, standard deviation, and the
+/// numbers of valid and nodata cells). For GeoTIFF files, the summary also describes the layout of
+/// the file, including the compression method, predictor, tiling or strip organization, and the
+/// number of images (e.g. overviews) contained in the file. A 256-bin histogram of the raster values
+/// may optionally be included (`--histogram`).
+///
+/// The summary is printed to the standard output, which makes it straightforward to parse from QC
+/// scripts and to include in bug reports, or, if an output file (`--output`) is specified, written to
+/// a JSON file. Statistics are read from the raster's `.aux.xml` sidecar file when it is up to date,
+/// and are otherwise calculated.
+///
+/// # See Also
+/// `PrintGeoTiffTags`, `RasterSummaryStats`, `LidarInfo`
+pub struct RasterSummary {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RasterSummary {
+    pub fn new() -> RasterSummary {
+        // public constructor
+        let name = "RasterSummary".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Reports the header information, CRS, data type, statistics, and file layout of a raster as JSON."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Raster File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output JSON File (optional)".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output JSON file; if unspecified, the summary is printed.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Include histogram?".to_owned(),
+            flags: vec!["--histogram".to_owned()],
+            description: "Include a 256-bin histogram of the raster values.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=DEM_summary.json --histogram",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        RasterSummary {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for RasterSummary {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        _reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let include_histogram = parsed.get_bool("histogram");
+
+        if verbose && !output_file.is_empty() {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        let input = Raster::new(&input_file, "r")?;
+        let configs = &input.configs;
+        let stats = input.get_statistics();
+
+        // JSON has no representation of NaN or infinite values
+        let num = |v: f64| {
+            if v.is_finite() {
+                json!(v)
+            } else {
+                serde_json::Value::Null
+            }
+        };
+
+        let file_size = fs::metadata(&input_file).map(|m| m.len()).unwrap_or(0);
+        let valid_percent = if stats.num_cells > 0 {
+            100f64 * stats.num_valid_cells as f64 / stats.num_cells as f64
+        } else {
+            0f64
+        };
+        let mut statistics = json!({
+            "minimum": num(stats.minimum),
+            "maximum": num(stats.maximum),
+            "mean": num(stats.mean),
+            "std_dev": num(stats.std_dev),
+            "num_valid_cells": stats.num_valid_cells,
+            "num_nodata_cells": stats.num_cells - stats.num_valid_cells,
+            "valid_percent": valid_percent,
+        });
+        if include_histogram {
+            statistics["histogram"] = json!({
+                "num_bins": HISTOGRAM_BINS,
+                "bin_width": num(stats.bin_width()),
+                "counts": stats.histogram,
+            });
+        }
+
+        let mut summary = json!({
+            "file": input_file,
+            "format": format!("{:?}", input.raster_type),
+            "file_size_bytes": file_size,
+            "rows": configs.rows,
+            "columns": configs.columns,
+            "bands": configs.bands,
+            "resolution_x": configs.resolution_x,
+            "resolution_y": configs.resolution_y,
+            "extent": {
+                "north": configs.north,
+                "south": configs.south,
+                "east": configs.east,
+                "west": configs.west,
+            },
+            "data_type": format!("{:?}", configs.data_type),
+            "photometric_interpretation": format!("{:?}", configs.photometric_interp),
+            "nodata": num(configs.nodata),
+            "z_units": configs.z_units,
+            "xy_units": configs.xy_units,
+            "pixel_is_area": configs.pixel_is_area,
+            "crs": {
+                "epsg": configs.epsg_code,
+                "wkt": configs.coordinate_ref_system_wkt,
+                "geographic": input.is_in_geographic_coordinates(),
+            },
+            "display_min": num(configs.display_min),
+            "display_max": num(configs.display_max),
+            "palette": configs.palette,
+            "statistics": statistics,
+            "metadata": configs.metadata,
+        });
+
+        if input.raster_type == RasterType::GeoTiff {
+            let layout = read_tiff_layout(&input_file)?;
+            summary["geotiff"] = json!({
+                "big_tiff": layout.is_big_tiff,
+                "byte_order": format!("{:?}", layout.endian),
+                "compression": layout.compression_name(),
+                "compression_code": layout.compression,
+                "predictor": layout.predictor,
+                "planar_configuration": layout.planar_configuration,
+                "samples_per_pixel": layout.samples_per_pixel,
+                "bits_per_sample": layout.bits_per_sample,
+                "sample_format": layout.sample_format,
+                "organization": if layout.is_tiled { "tiled" } else { "strips" },
+                "tile_width": layout.tile_width,
+                "tile_length": layout.tile_length,
+                "rows_per_strip": layout.rows_per_strip,
+                "num_blocks": layout.num_blocks,
+                "num_images": layout.num_images,
+            });
+        }
+
+        let json_str = match serde_json::to_string_pretty(&summary) {
+            Ok(s) => s,
+            Err(e) => return Err(Error::new(ErrorKind::Other, format!("{}", e))),
+        };
+
+        if output_file.is_empty() {
+            println!("{}", json_str);
+        } else {
+            let mut f = File::create(&output_file)?;
+            f.write_all(json_str.as_bytes())?;
+            if verbose {
+                println!("Output file written");
+            }
+        }
+
+        Ok(())
+    }
+}
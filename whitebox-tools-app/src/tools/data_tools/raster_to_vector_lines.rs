@@ -14,7 +14,7 @@ use whitebox_vector::*;
 use std::collections::VecDeque;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool converts raster lines features into a vector of the POLYLINE ShapeType.
@@ -137,40 +137,11 @@ impl WhiteboxTool for RasterToVectorLines {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -183,17 +154,11 @@ impl WhiteboxTool for RasterToVectorLines {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -237,11 +202,11 @@ impl WhiteboxTool for RasterToVectorLines {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata(z, nodata) {
                     count = 0i8;
                     for i in 0..8 {
                         zn = input.get_value(row + dy[i], col + dx[i]);
-                        if zn != 0f64 && zn != nodata {
+                        if zn != 0f64 && !is_nodata(zn, nodata) {
                             count += 1;
                         }
                     }
@@ -254,10 +219,11 @@ impl WhiteboxTool for RasterToVectorLines {
                     num_cells += 1;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -343,10 +309,11 @@ impl WhiteboxTool for RasterToVectorLines {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -423,12 +390,13 @@ impl WhiteboxTool for RasterToVectorLines {
 
                         current_id += 1;
 
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * num_solved_cells as f64
                                 / (num_cells - 1) as f64)
                                 as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -0,0 +1,293 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::Error;
+use std::path;
+
+/// This tool exports the grid cells of a raster (`--input`) to a delimited XYZ/CSV text file (`--output`)
+/// for interchange with surveying and bathymetric software. Each line of the output contains the x and y
+/// coordinates of a cell centre and the cell value. The field delimiter (`--delimiter`) may be 'comma',
+/// 'space', 'tab', or 'semicolon'. A header line naming the fields (X, Y, and Z) is written when the
+/// `--header` flag is specified.
+///
+/// Nodata cells are excluded from the output unless the `--include_nodata` flag is specified. The output
+/// may be subsampled by writing every nth row and column (`--step`), which is useful for reducing the size
+/// of the output for large rasters. The number of decimal places used for the cell values may be set using
+/// the `--decimals` parameter; coordinates are written with the precision needed to represent the cell centres.
+///
+/// # See Also
+/// `XyzToRaster`, `RasterToVectorPoints`, `ExportTableToCsv`
+pub struct RasterToXyz {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RasterToXyz {
+    pub fn new() -> RasterToXyz {
+        // public constructor
+        let name = "RasterToXyz".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description = "Exports the cells of a raster to a delimited XYZ/CSV text file.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Raster File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output XYZ/CSV File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output delimited text file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Delimiter".to_owned(),
+            flags: vec!["--delimiter".to_owned()],
+            description: "Field delimiter; options include 'comma', 'space', 'tab', and 'semicolon'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "comma".to_owned(),
+                "space".to_owned(),
+                "tab".to_owned(),
+                "semicolon".to_owned(),
+            ]),
+            default_value: Some("comma".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Write header line?".to_owned(),
+            flags: vec!["--header".to_owned()],
+            description: "Write a header line naming the fields.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Include nodata cells?".to_owned(),
+            flags: vec!["--include_nodata".to_owned()],
+            description: "Include nodata cells in the output.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Subsampling Step".to_owned(),
+            flags: vec!["--step".to_owned()],
+            description: "Write every nth row and column.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Decimal Places (optional)".to_owned(),
+            flags: vec!["--decimals".to_owned()],
+            description: "Number of decimal places used for cell values; by default, full precision is used.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=bathymetry.tif -o=bathymetry.xyz --delimiter=space --step=2 --decimals=2",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        RasterToXyz {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for RasterToXyz {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut delimiter = String::from(",");
+        let write_header = parsed.get_bool("header");
+        let include_nodata = parsed.get_bool("include_nodata");
+        let mut step = parsed.get_i64("step").map(|v| v as isize).unwrap_or(1isize);
+        let mut decimals: Option<usize> = None;
+
+        if let Some(value) = parsed.get_string("delimiter") {
+            let val = value.to_lowercase();
+            delimiter = if val.contains("space") {
+                " ".to_string()
+            } else if val.contains("tab") {
+                "\t".to_string()
+            } else if val.contains("semi") {
+                ";".to_string()
+            } else {
+                ",".to_string()
+            };
+        }
+        if let Some(value) = parsed.get_i64("decimals") {
+            decimals = Some(value as usize);
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        if step < 1 {
+            step = 1;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        // Coordinates are written with enough decimal places to resolve the cell centres.
+        let min_res = input.configs.resolution_x.min(input.configs.resolution_y);
+        let coord_decimals = if min_res > 0f64 {
+            ((-(min_res / 2f64).log10()).ceil().max(0f64) as usize).min(12)
+        } else {
+            6
+        };
+
+        let f = File::create(&output_file)?;
+        let mut writer = BufWriter::new(f);
+        if write_header {
+            writer.write_all(format!("X{0}Y{0}Z\n", delimiter).as_bytes())?;
+        }
+
+        let mut num_written = 0usize;
+        let mut z: f64;
+        for row in (0..rows).step_by(step as usize) {
+            let y = input.get_y_from_row(row);
+            for col in (0..columns).step_by(step as usize) {
+                z = input.get_value(row, col);
+                if !include_nodata && is_nodata(z, nodata) {
+                    continue;
+                }
+                let x = input.get_x_from_column(col);
+                let z_str = match decimals {
+                    Some(d) if !is_nodata(z, nodata) => format!("{:.*}", d, z),
+                    _ => format!("{}", z),
+                };
+                writer.write_all(
+                    format!(
+                        "{:.*}{}{:.*}{}{}\n",
+                        coord_decimals, x, delimiter, coord_decimals, y, delimiter, z_str
+                    )
+                    .as_bytes(),
+                )?;
+                num_written += 1;
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        writer.flush()?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Number of points written: {}", num_written);
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
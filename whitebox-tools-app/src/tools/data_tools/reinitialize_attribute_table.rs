@@ -9,7 +9,7 @@ License: MIT
 use crate::tools::*;
 use whitebox_vector::*;
 use std::env;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// Reinitializes a vector's attribute table deleting all fields but the feature ID (FID).
@@ -110,34 +110,12 @@ impl WhiteboxTool for ReinitializeAttributeTable {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
         let mut progress: usize;
         let mut old_progress: usize = 1;
@@ -155,9 +133,7 @@ impl WhiteboxTool for ReinitializeAttributeTable {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
         let input = Shapefile::read(&input_file)?;
 
@@ -176,11 +152,12 @@ impl WhiteboxTool for ReinitializeAttributeTable {
                 .attributes
                 .add_record(vec![FieldData::Int(record_num as i32 + 1i32)], false);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,635 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::spatial_ref_system::{Crs, Transformation};
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::f64::consts::PI;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+use whitebox_common::errors::ErrorCause;
+
+/// This tool reprojects a raster (`--input`) into another coordinate reference system (`--target_crs`),
+/// which may be given as an EPSG code (e.g. `EPSG:32617`), as a PROJ string (e.g. `+proj=utm +zone=17
+/// +datum=WGS84`), or as WKT, either directly or in a .prj file. The supported coordinate reference
+/// systems are geographic coordinates, Web Mercator, and the UTM zones, on the WGS84, NAD83, or ETRS89
+/// datums, which are treated as equivalent, since they differ by no more than about a metre or two; no
+/// datum shifts are applied, and other datums (e.g. NAD27 or ED50) are not supported. The tool does not
+/// use an EPSG database, and so the only EPSG codes it recognizes are 4326, 4269, 4258, and 4617
+/// (geographic), 3857 (Web Mercator), 32601-32660 and 32701-32760 (WGS84 UTM), 26901-26923 (NAD83 UTM),
+/// and 25828-25838 (ETRS89 UTM); other systems must be given as WKT or a PROJ string. The coordinate reference system of the input is read from the file, but may be
+/// specified (`--source_crs`) when the file lacks one or it is incorrect.
+///
+/// Each cell of the output is assigned the value of the input at the location of its centre, found by
+/// transforming the centre into the input's coordinate reference system, i.e. by the inverse of the
+/// transformation's PROJ-style pipeline (the inverse of one projection followed by another), which is
+/// recorded in the output's metadata. The value is interpolated using one of several resampling kernels
+/// (`--method`): nearest neighbour (`nn`), `bilinear`, cubic convolution (`cc`), or `lanczos` (a
+/// 3-lobed Lanczos window). With the default, `auto`, nearest neighbour is used for integer and
+/// categorical inputs, e.g. land cover classes, for which interpolated values would be meaningless, and
+/// bilinear interpolation otherwise. Input NoData cells are excluded from the interpolation, and output
+/// cells whose nearest input cell is NoData are assigned NoData.
+///
+/// By default, the output covers the transformed extent of the input, with square cells of a size
+/// (`--resolution`) that preserves the number of cells along the input's diagonal. The cell size, in the
+/// units of the target coordinate reference system, and the extent of the output (`--extent`, given as
+/// west, south, east, and north coordinates in the target coordinate reference system) may also be set.
+/// Alternatively, the output may take both the coordinate reference system and the grid of an existing
+/// raster (`--base`), e.g. so that the reprojected input can be combined with it in other tools.
+///
+/// # See Also
+/// `Resample`, `ExportWebTiles`
+pub struct ReprojectRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ReprojectRaster {
+    pub fn new() -> ReprojectRaster {
+        // public constructor
+        let name = "ReprojectRaster".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description = "Reprojects a raster into another coordinate reference system.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Target Coordinate Reference System".to_owned(),
+            flags: vec!["--target_crs".to_owned()],
+            description: "Target coordinate reference system, as an EPSG code (e.g. EPSG:32617), PROJ string, WKT, or .prj file.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Source Coordinate Reference System (optional)".to_owned(),
+            flags: vec!["--source_crs".to_owned()],
+            description: "Coordinate reference system of the input, if it is missing from or incorrect in the file.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Resampling Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Resampling method; options include 'auto', 'nn' (nearest neighbour), 'bilinear', 'cc' (cubic convolution), and 'lanczos'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "auto".to_owned(),
+                "nn".to_owned(),
+                "bilinear".to_owned(),
+                "cc".to_owned(),
+                "lanczos".to_owned(),
+            ]),
+            default_value: Some("auto".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Cell Size (optional)".to_owned(),
+            flags: vec!["--resolution".to_owned()],
+            description: "Output cell size, in the units of the target coordinate reference system.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Extent (optional)".to_owned(),
+            flags: vec!["--extent".to_owned()],
+            description: "Output extent as west,south,east,north in the target coordinate reference system.".to_owned(),
+            parameter_type: ParameterType::StringList,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Raster whose coordinate reference system and grid the output takes.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem_geographic.tif -o=dem_utm.tif --target_crs=EPSG:32617 --method=cc --resolution=30
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=landcover.tif -o=landcover_aligned.tif --base=dem_utm.tif --method=nn", short_exe, name).replace("*", &sep);
+
+        ReprojectRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ReprojectRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let target_crs = parsed.get_string("target_crs").unwrap_or_default();
+        let source_crs = parsed.get_string("source_crs").unwrap_or_default();
+        let mut method = parsed.get_string("method").unwrap_or("auto".to_string()).to_lowercase();
+        let resolution = parsed.get_f64("resolution");
+        let extent = parsed.get_f64_list("extent")?;
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if target_crs.trim().is_empty() && base_file.trim().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either the target coordinate reference system (--target_crs) or a base raster (--base) must be specified.",
+            ));
+        }
+        if !extent.is_empty() && (extent.len() != 4 || extent[0] >= extent[2] || extent[1] >= extent[3]) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The output extent must be given as west,south,east,north, with west less than east and south less than north.",
+            ));
+        }
+        if let Some(res) = resolution {
+            if !(res > 0f64) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The output cell size must be greater than zero.",
+                ));
+            }
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let source = if !source_crs.trim().is_empty() {
+            parse_crs(&source_crs, working_directory)?
+        } else {
+            Crs::from_definition(
+                input.configs.epsg_code,
+                &input.configs.coordinate_ref_system_wkt,
+                input.is_in_geographic_coordinates(),
+            )
+            .ok_or_else(|| {
+                ErrorCause::UnsupportedFormat.error(
+                    format!(
+                        "The coordinate reference system of the input (EPSG code {}) is unknown or not supported; it may be specified with --source_crs.",
+                        input.configs.epsg_code
+                    ),
+                )
+            })?
+        };
+
+        // The grid of the output, either that of the base raster or one covering the input.
+        let base = if !base_file.trim().is_empty() {
+            base_file = resolve_input_path(&base_file, working_directory)?;
+            Some(Raster::new(&base_file, "r")?)
+        } else {
+            None
+        };
+        let (target, mut configs) = match &base {
+            Some(base) => {
+                if verbose && !target_crs.trim().is_empty() {
+                    println!("Warning: The output takes the coordinate reference system of the base raster, and --target_crs is ignored.");
+                }
+                let target = Crs::from_definition(
+                    base.configs.epsg_code,
+                    &base.configs.coordinate_ref_system_wkt,
+                    base.is_in_geographic_coordinates(),
+                )
+                .ok_or_else(|| {
+                    ErrorCause::UnsupportedFormat.error(
+                        format!(
+                            "The coordinate reference system of the base raster (EPSG code {}) is unknown or not supported.",
+                            base.configs.epsg_code
+                        ),
+                    )
+                })?;
+                (target, base.configs.clone())
+            }
+            None => {
+                let target = parse_crs(&target_crs, working_directory)?;
+                (target, output_grid(&input, source, target, resolution, &extent)?)
+            }
+        };
+        configs.nodata = input.configs.nodata;
+        configs.data_type = input.configs.data_type;
+        configs.photometric_interp = input.configs.photometric_interp;
+        configs.palette = input.configs.palette.clone();
+        configs.z_units = input.configs.z_units.clone();
+        configs.metadata = vec![];
+        if base.is_none() {
+            configs.epsg_code = target.epsg_code();
+            configs.coordinate_ref_system_wkt = target.wkt();
+            configs.xy_units = if target.is_geographic() { "degrees" } else { "metres" }.to_string();
+        }
+
+        let categorical = !input.configs.data_type.is_float()
+            || input.configs.photometric_interp == PhotometricInterpretation::Categorical
+            || input.configs.photometric_interp == PhotometricInterpretation::RGB;
+        if method == "auto" {
+            method = if categorical { "nn" } else { "bilinear" }.to_string();
+        }
+        let kernel = match method.as_str() {
+            "nn" | "nearest" => Kernel::Nearest,
+            "bilinear" => Kernel::Bilinear,
+            "cc" | "cubic" => Kernel::Cubic,
+            "lanczos" => Kernel::Lanczos,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unrecognized resampling method '{}'.", method),
+                ))
+            }
+        };
+        if kernel != Kernel::Nearest {
+            if input.configs.photometric_interp == PhotometricInterpretation::RGB {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Colour (RGB) rasters can only be reprojected using nearest neighbour resampling (--method=nn).",
+                ));
+            }
+            if !input.configs.data_type.is_float() {
+                // interpolated values are not integers
+                configs.data_type = DataType::F32;
+            }
+        }
+
+        let transformation = Transformation::new(source, target);
+        if verbose {
+            println!("Source: {}", source.proj_string());
+            println!("Target: {}", target.proj_string());
+            println!("Output: {} rows x {} columns, cell size {}", configs.rows, configs.columns, configs.resolution_x);
+        }
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        let rows = configs.rows as isize;
+        let columns = configs.columns as isize;
+        let nodata = configs.nodata;
+
+        let input = Arc::new(input);
+        let inverse = transformation.inverse();
+        let out_configs = Arc::new(configs.clone());
+        let num_procs = whitebox_common::utils::ThreadPool::new(&whitebox_common::configs::get_configs()?).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let out_configs = out_configs.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let c = &input.configs;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let y = out_configs.north - (row as f64 + 0.5) * out_configs.resolution_y;
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        let x = out_configs.west + (col as f64 + 0.5) * out_configs.resolution_x;
+                        let (sx, sy) = inverse.transform(x, y);
+                        if !sx.is_finite() || !sy.is_finite() {
+                            continue;
+                        }
+                        // the position in the input, in units of cells, with cell centres at integers
+                        let col_src = (sx - c.west) / c.resolution_x - 0.5;
+                        let row_src = (c.north - sy) / c.resolution_y - 0.5;
+                        if let Some(z) = kernel.sample(&input, row_src, col_src) {
+                            data[col as usize] = z;
+                        }
+                    }
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!(
+            "Reprojected from EPSG:{} to EPSG:{}",
+            source.epsg_code(),
+            target.epsg_code()
+        ));
+        output.add_metadata_entry(format!("PROJ pipeline: {}", transformation.pipeline()));
+        output.add_metadata_entry(format!("Resampling method: {}", method));
+        if base.is_some() {
+            output.add_metadata_entry(format!("Base file: {}", base_file));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a coordinate reference system, reading it from a .prj file if one is named.
+fn parse_crs(definition: &str, working_directory: &str) -> Result<Crs, Error> {
+    let definition = definition.trim();
+    if definition.to_lowercase().ends_with(".prj") {
+        let file = resolve_input_path(definition, working_directory)?;
+        return Crs::parse(&fs::read_to_string(file)?);
+    }
+    Crs::parse(definition)
+}
+
+/// Returns the grid of the output, covering the extent of the input transformed into the target
+/// coordinate reference system unless an extent is given, with square cells of the given size or,
+/// by default, of the size that preserves the number of cells along the input's diagonal.
+fn output_grid(
+    input: &Raster,
+    source: Crs,
+    target: Crs,
+    resolution: Option<f64>,
+    extent: &[f64],
+) -> Result<RasterConfigs, Error> {
+    let c = &input.configs;
+    let transformation = Transformation::new(source, target);
+    // The transformed extent is found from points along the edges of the input, since the edges
+    // are generally curved in the target coordinate reference system.
+    let (mut west, mut south, mut east, mut north) =
+        (f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY);
+    let n = 50;
+    for i in 0..=n {
+        let t = i as f64 / n as f64;
+        let x = c.west + t * (c.east - c.west);
+        let y = c.south + t * (c.north - c.south);
+        for (px, py) in [(x, c.south), (x, c.north), (c.west, y), (c.east, y)] {
+            let (tx, ty) = transformation.transform(px, py);
+            if tx.is_finite() && ty.is_finite() {
+                west = west.min(tx);
+                east = east.max(tx);
+                south = south.min(ty);
+                north = north.max(ty);
+            }
+        }
+    }
+    if !(west < east && south < north) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The extent of the input could not be transformed into the target coordinate reference system.",
+        ));
+    }
+    let resolution = match resolution {
+        Some(res) => res,
+        None => {
+            let cells = ((c.rows * c.rows + c.columns * c.columns) as f64).sqrt();
+            ((east - west).powi(2) + (north - south).powi(2)).sqrt() / cells
+        }
+    };
+    if extent.len() == 4 {
+        west = extent[0];
+        south = extent[1];
+        east = extent[2];
+        north = extent[3];
+    }
+    let columns = ((east - west) / resolution).ceil().max(1f64) as usize;
+    let rows = ((north - south) / resolution).ceil().max(1f64) as usize;
+    if target.is_geographic() && (west < -180f64 - resolution || east > 180f64 + resolution) {
+        println!("Warning: The output extends beyond the range of longitudes (-180 to 180).");
+    }
+    if target == Crs::WebMercator && north.abs().max(south.abs()) > PI * whitebox_common::spatial_ref_system::EARTH_RADIUS {
+        println!("Warning: The output extends beyond the latitudes that the Web Mercator projection can represent.");
+    }
+    Ok(RasterConfigs {
+        rows,
+        columns,
+        west,
+        north,
+        east: west + columns as f64 * resolution,
+        south: north - rows as f64 * resolution,
+        resolution_x: resolution,
+        resolution_y: resolution,
+        ..Default::default()
+    })
+}
+
+/// The resampling kernels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kernel {
+    Nearest,
+    Bilinear,
+    Cubic,
+    Lanczos,
+}
+
+impl Kernel {
+    /// The number of cells either side of the sampled position that the kernel spans.
+    fn radius(&self) -> isize {
+        match self {
+            Kernel::Nearest => 0,
+            Kernel::Bilinear => 1,
+            Kernel::Cubic => 2,
+            Kernel::Lanczos => 3,
+        }
+    }
+
+    /// The weight of a cell at a distance, in cells, from the sampled position.
+    fn weight(&self, d: f64) -> f64 {
+        let d = d.abs();
+        match self {
+            Kernel::Nearest => 1f64,
+            Kernel::Bilinear => (1f64 - d).max(0f64),
+            // Keys' cubic convolution, with a = -0.5
+            Kernel::Cubic => {
+                if d <= 1f64 {
+                    1.5 * d * d * d - 2.5 * d * d + 1f64
+                } else if d < 2f64 {
+                    -0.5 * d * d * d + 2.5 * d * d - 4f64 * d + 2f64
+                } else {
+                    0f64
+                }
+            }
+            Kernel::Lanczos => {
+                if d < 1e-12 {
+                    1f64
+                } else if d < 3f64 {
+                    let x = PI * d;
+                    3f64 * x.sin() * (x / 3f64).sin() / (x * x)
+                } else {
+                    0f64
+                }
+            }
+        }
+    }
+
+    /// Samples the input at a position given in units of cells, with cell centres at integers.
+    /// Returns None where the nearest cell is outside of the input or NoData. NoData cells are
+    /// excluded from the interpolation, with the weights of the remaining cells normalized.
+    fn sample(&self, input: &Raster, row: f64, col: f64) -> Option<f64> {
+        let c = &input.configs;
+        let nearest_row = row.round();
+        let nearest_col = col.round();
+        if nearest_row < 0f64 || nearest_col < 0f64 || nearest_row >= c.rows as f64 || nearest_col >= c.columns as f64 {
+            return None;
+        }
+        let z = input.get_value(nearest_row as isize, nearest_col as isize);
+        if is_nodata(z, c.nodata) {
+            return None;
+        }
+        if *self == Kernel::Nearest {
+            return Some(z);
+        }
+        let radius = self.radius();
+        let (row0, col0) = (row.floor() as isize, col.floor() as isize);
+        let (mut sum, mut sum_weights) = (0f64, 0f64);
+        for r in (row0 - radius + 1)..=(row0 + radius) {
+            if r < 0 || r >= c.rows as isize {
+                continue;
+            }
+            let wy = self.weight(row - r as f64);
+            if wy == 0f64 {
+                continue;
+            }
+            for cc in (col0 - radius + 1)..=(col0 + radius) {
+                if cc < 0 || cc >= c.columns as isize {
+                    continue;
+                }
+                let z = input.get_value(r, cc);
+                if is_nodata(z, c.nodata) {
+                    continue;
+                }
+                let w = wy * self.weight(col - cc as f64);
+                sum += w * z;
+                sum_weights += w;
+            }
+        }
+        if sum_weights.abs() < 1e-12 {
+            return Some(z);
+        }
+        Some(sum / sum_weights)
+    }
+}
@@ -11,14 +11,13 @@ use crate::tools::ParameterFileType;
 use crate::tools::ParameterType;
 use crate::tools::ToolParameter;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool will re-assign a user-defined background value in an input raster image the **NoData** value.
 /// More precisely, the NoData value will be changed to the specified background value and any existing
@@ -141,47 +140,12 @@ impl WhiteboxTool for SetNodataValue {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut back_value = 0f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-back_value" {
-                back_value = if keyval {
-                    vec[1].to_string().parse().unwrap()
-                } else {
-                    args[i + 1].parse().unwrap()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let back_value = parsed.get_f64("back_value").unwrap_or(0f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -194,14 +158,8 @@ impl WhiteboxTool for SetNodataValue {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Arc::new(Raster::new(&input_file, "r")?);
 
@@ -216,27 +174,25 @@ impl WhiteboxTool for SetNodataValue {
         let mut output = Raster::initialize_using_file(&output_file, &input);
         output.configs.nodata = back_value;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![back_value; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != back_value && z != nodata {
+                        if !is_nodata(z, back_value) && !is_nodata(z, nodata) {
                             data[col as usize] = z;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -244,10 +200,11 @@ impl WhiteboxTool for SetNodataValue {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -153,52 +153,20 @@ impl WhiteboxTool for SinglePartToMultiPart {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
         let mut field_name = String::new();
         let mut use_field = false;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                field_name = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                use_field = true;
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
+        if let Some(value) = parsed.get_string("field") {
+            field_name = value;
+            use_field = true;
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -215,13 +183,9 @@ impl WhiteboxTool for SinglePartToMultiPart {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Shapefile::read(&input_file)?;
 
@@ -274,12 +238,13 @@ impl WhiteboxTool for SinglePartToMultiPart {
                             z_values.push(record.z_array[0]);
                         }
 
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (record_num + 1) as f64
                                 / input.num_records as f64)
                                 as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -351,12 +316,13 @@ impl WhiteboxTool for SinglePartToMultiPart {
                             }
                         }
 
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (record_num + 1) as f64
                                 / input.num_records as f64)
                                 as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -398,6 +364,7 @@ impl WhiteboxTool for SinglePartToMultiPart {
                     // polygons contained within other polygons will be considered holes
                     let mut is_contained = vec![false; input.num_records];
                     for record_num in 0..input.num_records {
+                        reporter.check_cancelled()?;
                         let record1 = input.get_record(record_num);
                         for i in 0..input.num_records {
                             if i != record_num {
@@ -453,12 +420,13 @@ impl WhiteboxTool for SinglePartToMultiPart {
                             }
                         }
 
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (record_num + 1) as f64
                                 / input.num_records as f64)
                                 as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -552,6 +520,7 @@ impl WhiteboxTool for SinglePartToMultiPart {
                     let mut feature_num = vec![input.num_records + 1; input.num_records];
                     let mut id = 0usize;
                     for record_num in 0..input.num_records {
+                        reporter.check_cancelled()?;
                         if feature_num[record_num] == input.num_records + 1 {
                             feature_num[record_num] = id;
                             let record1 = input.attributes.get_value(record_num, &field_name);
@@ -615,10 +584,11 @@ impl WhiteboxTool for SinglePartToMultiPart {
                             false,
                         );
 
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (id + 1) as f64 / max_id as f64) as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -659,6 +629,7 @@ impl WhiteboxTool for SinglePartToMultiPart {
                     let mut feature_num = vec![input.num_records + 1; input.num_records];
                     let mut id = 0usize;
                     for record_num in 0..input.num_records {
+                        reporter.check_cancelled()?;
                         if feature_num[record_num] == input.num_records + 1 {
                             feature_num[record_num] = id;
                             let record1 = input.attributes.get_value(record_num, &field_name);
@@ -722,10 +693,11 @@ impl WhiteboxTool for SinglePartToMultiPart {
                             false,
                         );
 
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (id + 1) as f64 / max_id as f64) as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -766,6 +738,7 @@ impl WhiteboxTool for SinglePartToMultiPart {
                     let mut feature_num = vec![input.num_records + 1; input.num_records];
                     let mut id = 0usize;
                     for record_num in 0..input.num_records {
+                        reporter.check_cancelled()?;
                         if feature_num[record_num] == input.num_records + 1 {
                             feature_num[record_num] = id;
                             let record1 = input.attributes.get_value(record_num, &field_name);
@@ -784,6 +757,7 @@ impl WhiteboxTool for SinglePartToMultiPart {
                     // polygons contained within other polygons with the same field id will be considered holes
                     let mut is_contained = vec![false; input.num_records];
                     for record_num in 0..input.num_records {
+                        reporter.check_cancelled()?;
                         let record1 = input.get_record(record_num);
                         for record_num2 in 0..input.num_records {
                             if record_num2 != record_num
@@ -861,10 +835,11 @@ impl WhiteboxTool for SinglePartToMultiPart {
                             false,
                         );
 
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (id + 1) as f64 / max_id as f64) as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -0,0 +1,476 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{interior_point, point_in_poly};
+use whitebox_common::structures::{BoundingBox, Point2D};
+use crate::tools::*;
+use whitebox_vector::*;
+use std::collections::HashMap;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool can be used to split a vector layer into multiple output files, which is useful for
+/// batch processing, e.g. running an analysis for each watershed or county in a coverage. Features
+/// can be split in either of two ways. When a `--field` is specified, one output file is created for
+/// each unique value of that field within the input attribute table. Alternatively, when a polygon
+/// `--grid` file (e.g. the output of `CreateRectangularVectorGrid`) is specified, one output file is
+/// created for each grid tile, containing those features whose representative point falls within the
+/// tile. The representative point of a feature is its first point (points and multipoints), the middle
+/// vertex of its first part (lines), or an interior point of its first part (polygons). Features that
+/// do not fall within any tile are excluded from the outputs. Tiles are named using the optional
+/// `--grid_field`, or by their record number otherwise.
+///
+/// Output files are written to the output directory (`--outdir`), which defaults to the working
+/// directory, and are named using the pattern PREFIX_VALUE.shp, where PREFIX defaults to the name
+/// of the input file. Any characters in the value that are not suitable for a file name are replaced
+/// by underscores. Each output file contains the full attribute table of the input layer.
+///
+/// # See Also
+/// `MergeVectors`, `CreateRectangularVectorGrid`
+pub struct SplitVectorLayer {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SplitVectorLayer {
+    pub fn new() -> SplitVectorLayer {
+        // public constructor
+        let name = "SplitVectorLayer".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Splits a vector layer into separate files by the unique values of a field or by the tiles of a grid."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Split Field".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Input field used to split the layer (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Grid Polygon File".to_owned(),
+            flags: vec!["--grid".to_owned()],
+            description: "Input vector polygon grid used to split the layer (optional).".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Grid Name Field".to_owned(),
+            flags: vec!["--grid_field".to_owned()],
+            description: "Grid field used to name the output tiles (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--grid".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Directory".to_owned(),
+            flags: vec!["--outdir".to_owned()],
+            description: "Output directory (optional; defaults to the working directory).".to_owned(),
+            parameter_type: ParameterType::Directory,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File Name Prefix".to_owned(),
+            flags: vec!["--prefix".to_owned()],
+            description: "Output file name prefix (optional; defaults to the input file name).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=watersheds.shp --field=BASIN_ID --outdir=\"*path*to*output*\"
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=roads.shp --grid=tiles.shp --grid_field=TILE --prefix=roads",
+            short_exe, name
+        ).replace("*", &sep);
+
+        SplitVectorLayer {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SplitVectorLayer {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let field_name = parsed.get_string("field").unwrap_or_default();
+        let mut grid_file = parsed.get_string("grid").unwrap_or_default();
+        let grid_field = parsed.get_string("grid_field").unwrap_or_default();
+        let mut output_directory = parsed.get_string("outdir").unwrap_or_default();
+        let mut prefix = parsed.get_string("prefix").unwrap_or_default();
+
+        // read the arguments
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if field_name.is_empty() && grid_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either a split field (--field) or a grid file (--grid) must be specified.",
+            ));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        grid_file = resolve_input_path(&grid_file, working_directory)?;
+        if output_directory.is_empty() {
+            output_directory = working_directory.to_string();
+        }
+        if !output_directory.is_empty()
+            && !output_directory.ends_with(&sep)
+            && !output_directory.ends_with("/")
+        {
+            output_directory.push_str(&sep);
+        }
+        if prefix.is_empty() {
+            prefix = path::Path::new(&input_file)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("split")
+                .to_string();
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        // Assign each of the input records to a group, keeping the groups in order of first appearance.
+        let mut group_names: Vec<String> = vec![];
+        let mut group_records: Vec<Vec<usize>> = vec![];
+        let mut group_lookup: HashMap<String, usize> = HashMap::new();
+        let mut num_unassigned = 0usize;
+
+        if !grid_file.is_empty() {
+            let grid = Shapefile::read(&grid_file)?;
+            if grid.header.shape_type.base_shape_type() != ShapeType::Polygon {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input grid file must be of a POLYGON base shape type.",
+                ));
+            }
+            if !grid_field.is_empty() && grid.attributes.get_field_num(&grid_field).is_none() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The grid field '{}' does not exist in the grid attribute table.", grid_field),
+                ));
+            }
+
+            let mut tile_bb: Vec<BoundingBox> = Vec::with_capacity(grid.num_records);
+            let mut tile_names: Vec<String> = Vec::with_capacity(grid.num_records);
+            for tile in 0..grid.num_records {
+                reporter.check_cancelled()?;
+                let record = grid.get_record(tile);
+                tile_bb.push(BoundingBox::new(
+                    record.x_min,
+                    record.x_max,
+                    record.y_min,
+                    record.y_max,
+                ));
+                tile_names.push(if grid_field.is_empty() {
+                    format!("tile{}", tile + 1)
+                } else {
+                    format!("{}", grid.attributes.get_value(tile, &grid_field))
+                });
+            }
+
+            for record_num in 0..input.num_records {
+                let record = input.get_record(record_num);
+                if record.shape_type == ShapeType::Null || record.num_points == 0 {
+                    num_unassigned += 1;
+                    continue;
+                }
+                let p = representative_point(record);
+                let mut found = false;
+                for tile in 0..grid.num_records {
+                    if tile_bb[tile].is_point_in_box(p.x, p.y)
+                        && is_point_in_record(&p, grid.get_record(tile))
+                    {
+                        add_to_group(
+                            &tile_names[tile],
+                            record_num,
+                            &mut group_names,
+                            &mut group_records,
+                            &mut group_lookup,
+                        );
+                        found = true;
+                        break;
+                    }
+                }
+                if !found {
+                    num_unassigned += 1;
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Assigning features to tiles", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        } else {
+            if input.attributes.get_field_num(&field_name).is_none() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The field '{}' does not exist in the input attribute table.", field_name),
+                ));
+            }
+            for record_num in 0..input.num_records {
+                reporter.check_cancelled()?;
+                if input.get_record(record_num).shape_type == ShapeType::Null {
+                    num_unassigned += 1;
+                    continue;
+                }
+                let value = format!("{}", input.attributes.get_value(record_num, &field_name));
+                add_to_group(
+                    &value,
+                    record_num,
+                    &mut group_names,
+                    &mut group_records,
+                    &mut group_lookup,
+                );
+            }
+        }
+
+        if group_names.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "None of the input features could be assigned to an output file.",
+            ));
+        }
+
+        // Create a unique, file-system safe name for each group.
+        let mut used_names: HashMap<String, usize> = HashMap::new();
+        let mut file_names: Vec<String> = Vec::with_capacity(group_names.len());
+        for name in &group_names {
+            let mut safe: String = name
+                .trim()
+                .chars()
+                .map(|c| {
+                    if c.is_alphanumeric() || c == '-' || c == '_' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            if safe.is_empty() {
+                safe = "blank".to_string();
+            }
+            let key = safe.to_lowercase();
+            let count = used_names.entry(key).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                safe = format!("{}_{}", safe, count);
+            }
+            file_names.push(format!("{}{}_{}.shp", output_directory, prefix, safe));
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let num_groups = group_names.len();
+        for g in 0..num_groups {
+            let mut output =
+                Shapefile::initialize_using_file(&file_names[g], &input, input.header.shape_type, true)?;
+            for &record_num in &group_records[g] {
+                output.add_record(input.get_record(record_num).clone());
+                output
+                    .attributes
+                    .add_record(input.attributes.get_record(record_num), false);
+            }
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!(
+                            "Output file written: {} ({} features)",
+                            file_names[g],
+                            group_records[g].len()
+                        );
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Number of output files: {}", num_groups);
+            if num_unassigned > 0 {
+                println!(
+                    "Warning: {} features were not assigned to an output file.",
+                    num_unassigned
+                );
+            }
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+fn add_to_group(
+    name: &str,
+    record_num: usize,
+    group_names: &mut Vec<String>,
+    group_records: &mut Vec<Vec<usize>>,
+    group_lookup: &mut HashMap<String, usize>,
+) {
+    match group_lookup.get(name) {
+        Some(&g) => group_records[g].push(record_num),
+        None => {
+            group_lookup.insert(name.to_string(), group_names.len());
+            group_names.push(name.to_string());
+            group_records.push(vec![record_num]);
+        }
+    }
+}
+
+/// Returns a point that is representative of the location of a feature.
+fn representative_point(record: &ShapefileGeometry) -> Point2D {
+    let part_end = if record.num_parts > 1 {
+        record.parts[1] as usize
+    } else {
+        record.num_points as usize
+    };
+    let points = &record.points[0..part_end];
+    match record.shape_type.base_shape_type() {
+        ShapeType::Polygon if points.len() > 3 && points[0].nearly_equals(&points[points.len() - 1]) => {
+            interior_point(points)
+        }
+        ShapeType::PolyLine => points[points.len() / 2],
+        _ => points[0],
+    }
+}
+
+/// Is the point within the polygon record, i.e. within one of its outer rings and not within a hole?
+fn is_point_in_record(p: &Point2D, record: &ShapefileGeometry) -> bool {
+    let mut in_poly = false;
+    for part in 0..record.num_parts as usize {
+        let start = record.parts[part] as usize;
+        let end = if part < record.num_parts as usize - 1 {
+            record.parts[part + 1] as usize
+        } else {
+            record.num_points as usize
+        };
+        if point_in_poly(p, &record.points[start..end]) {
+            if record.is_hole(part as i32) {
+                return false;
+            }
+            in_poly = true;
+        }
+    }
+    in_poly
+}
@@ -0,0 +1,316 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::structures::{Array2D, HexGrid};
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool resamples a raster on a square grid (`--input`) onto a grid of pointy-topped hexagons,
+/// for use with tools that operate on hexagonal grids, such as `HexFlowAccumulation`. On a square grid,
+/// the diagonal neighbours of a cell are 1.41 times further away than its cardinal neighbours, which
+/// biases flow routing and spread models toward the grid directions. Each cell of a hexagonal grid
+/// instead has six neighbours at the same distance.
+///
+/// The hexagonal grid is stored as an ordinary raster in an 'odd-r' offset layout: the raster's columns
+/// are spaced at the hexagon width (`--width`, which defaults to the input cell size) and its rows at
+/// *width* x sqrt(3) / 2, and the cells of every odd row (counting the top row as row 0) are centred half
+/// a cell east of the corresponding raster cell. As a result, the output displays as a slightly sheared
+/// image in most GIS, and it should only be used as input to the hexagonal grid tools, or converted back
+/// onto a square grid using `HexToSquareGrid`.
+///
+/// With the `nn` method (the default), each hexagon takes the value of the input cell containing its
+/// centre. With the `mean` method, each hexagon takes the mean of the input cells whose centres it
+/// contains, which is appropriate when the hexagons are larger than the input cells; hexagons that
+/// contain no input cell centres take the value of the cell containing their centre.
+///
+/// # See Also
+/// `HexToSquareGrid`, `HexFlowAccumulation`, `Resample`
+pub struct SquareToHexGrid {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SquareToHexGrid {
+    pub fn new() -> SquareToHexGrid {
+        // public constructor
+        let name = "SquareToHexGrid".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Resamples a square-grid raster onto a hexagonal grid stored in an offset (odd-r) layout.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output hexagonal grid raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Hexagon Width (optional)".to_owned(),
+            flags: vec!["--width".to_owned()],
+            description: "Distance between the centres of neighbouring hexagons; defaults to the input cell size.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Resampling method; 'nn' (nearest neighbour) or 'mean'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["nn".to_owned(), "mean".to_owned()]),
+            default_value: Some("nn".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif -o=dem_hex.tif --width=10.0 --method=mean",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SquareToHexGrid {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SquareToHexGrid {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut width = parsed.get_f64("width").unwrap_or(0f64);
+        let mut method = String::from("nn");
+
+        if let Some(value) = parsed.get_string("method") {
+            method = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let use_mean = if method.contains("mean") {
+            true
+        } else if method.contains("nn") || method.contains("near") {
+            false
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --method parameter must be either 'nn' or 'mean'.",
+            ));
+        };
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let nodata = input.configs.nodata;
+        if width <= 0f64 {
+            width = input.configs.resolution_x;
+        }
+        let grid = HexGrid::covering(
+            input.configs.west,
+            input.configs.north,
+            input.configs.east,
+            input.configs.south,
+            width,
+        );
+
+        let mut configs = input.configs.clone();
+        // the grid differs from the input's, so its GeoTIFF georeferencing tags don't apply
+        configs.model_tiepoint = vec![];
+        configs.model_pixel_scale = [0f64; 3];
+        configs.model_transformation = [0f64; 16];
+        configs.rows = grid.rows as usize;
+        configs.columns = grid.columns as usize;
+        configs.east = grid.east();
+        configs.south = grid.south();
+        configs.resolution_x = grid.width;
+        configs.resolution_y = grid.row_spacing();
+        if use_mean && configs.data_type != DataType::F32 && configs.data_type != DataType::F64 {
+            configs.data_type = DataType::F32;
+        }
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        let out_nodata = output.configs.nodata;
+
+        // For the mean method, accumulate the input cells within each hexagon.
+        let mut sum: Array2D<f64> = Array2D::new(grid.rows, grid.columns, 0f64, 0f64)?;
+        let mut count: Array2D<f64> = Array2D::new(grid.rows, grid.columns, 0f64, 0f64)?;
+        if use_mean {
+            let rows = input.configs.rows as isize;
+            let columns = input.configs.columns as isize;
+            for row in 0..rows {
+                let y = input.get_y_from_row(row);
+                for col in 0..columns {
+                    let z = input.get_value(row, col);
+                    if is_nodata(z, nodata) {
+                        continue;
+                    }
+                    if let Some((r, c)) = grid.cell_containing(input.get_x_from_column(col), y) {
+                        sum.increment(r, c, z);
+                        count.increment(r, c, 1f64);
+                    }
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Aggregating cells", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        }
+
+        for row in 0..grid.rows {
+            for col in 0..grid.columns {
+                let n = count.get_value(row, col);
+                let value = if n > 0f64 {
+                    sum.get_value(row, col) / n
+                } else {
+                    let (x, y) = grid.centre(row, col);
+                    let z = input.get_value(input.get_row_from_y(y), input.get_column_from_x(x));
+                    if is_nodata(z, nodata) {
+                        out_nodata
+                    } else {
+                        z
+                    }
+                };
+                output.set_value(row, col, value);
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (grid.rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!(
+            "Hexagonal grid: odd-r offset layout, width {}",
+            grid.width
+        ));
+        output.add_metadata_entry(format!("Method: {}", if use_mean { "mean" } else { "nn" }));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
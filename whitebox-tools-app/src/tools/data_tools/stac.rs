@@ -0,0 +1,185 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Helpers shared by the STAC (SpatioTemporal Asset Catalog) tools, StacSearch and
+ExportStacItems. STAC footprints and bounding boxes are given in WGS84 geographic
+coordinates, so the extents of files in projected coordinate systems are reprojected, for
+the coordinate reference systems that WhiteboxTools can reproject (see `Crs`).
+*/
+
+use whitebox_common::spatial_ref_system::Crs;
+use std::io::{Error, ErrorKind};
+use std::path::{Component, Path};
+use whitebox_raster::Raster;
+use whitebox_vector::Shapefile;
+
+/// The version of the STAC specification of the documents written by the tools.
+pub(super) const STAC_VERSION: &str = "1.0.0";
+
+const RASTER_EXTENSIONS: [&str; 13] = [
+    "tif", "tiff", "gtif", "gtiff", "dep", "bil", "flt", "rdc", "rst", "sdat", "sgrd", "grd", "asc",
+];
+
+/// The extent of a raster or vector file.
+pub(super) struct FileExtent {
+    /// The bounding box in the coordinates of the file (west, south, east, north).
+    pub bbox: [f64; 4],
+    pub epsg: u16,
+    /// The number of rows and columns of a raster.
+    pub shape: Option<[usize; 2]>,
+    /// The affine transform of a raster, in the order used by the STAC projection extension.
+    pub transform: Option<[f64; 6]>,
+    /// The corners of the extent in geographic coordinates, as a closed ring, if the coordinate
+    /// reference system of the file is supported.
+    pub footprint: Option<Vec<[f64; 2]>>,
+}
+
+impl FileExtent {
+    /// The geographic bounding box of the footprint (west, south, east, north).
+    pub fn geographic_bbox(&self) -> Option<[f64; 4]> {
+        let footprint = self.footprint.as_ref()?;
+        let mut bbox = [f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY];
+        for p in footprint {
+            bbox[0] = bbox[0].min(p[0]);
+            bbox[1] = bbox[1].min(p[1]);
+            bbox[2] = bbox[2].max(p[0]);
+            bbox[3] = bbox[3].max(p[1]);
+        }
+        Some(bbox)
+    }
+}
+
+/// Returns true if the file name has the extension of a raster format.
+pub(super) fn is_raster_file(file_name: &str) -> bool {
+    RASTER_EXTENSIONS.contains(&extension(file_name).as_str())
+}
+
+/// Returns true if the file name has the extension of a Shapefile.
+pub(super) fn is_vector_file(file_name: &str) -> bool {
+    extension(file_name) == "shp"
+}
+
+/// Returns the lower-case extension of a file name, or URL, without any query string.
+pub(super) fn extension(file_name: &str) -> String {
+    let file_name = file_name.split(|c| c == '?' || c == '#').next().unwrap_or("");
+    Path::new(file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase()
+}
+
+/// Reads the extent of a raster or vector file. Other kinds of files have no extent.
+pub(super) fn read_extent(file_name: &str) -> Result<Option<FileExtent>, Error> {
+    let (bbox, epsg, wkt, shape, transform) = if is_raster_file(file_name) {
+        let input = Raster::new(file_name, "r")?;
+        let c = &input.configs;
+        (
+            [c.west, c.south, c.east, c.north],
+            c.epsg_code,
+            c.coordinate_ref_system_wkt.clone(),
+            Some([c.rows, c.columns]),
+            Some([c.resolution_x, 0f64, c.west, 0f64, -c.resolution_y, c.north]),
+        )
+    } else if is_vector_file(file_name) {
+        let input = Shapefile::read(file_name)?;
+        let h = &input.header;
+        (
+            [h.x_min, h.y_min, h.x_max, h.y_max],
+            0u16,
+            input.projection.clone(),
+            None,
+            None,
+        )
+    } else {
+        return Ok(None);
+    };
+
+    // as with Raster::is_in_geographic_coordinates
+    let wkt_lower = wkt.to_lowercase();
+    let geographic = bbox[0] >= -180f64
+        && bbox[2] <= 180f64
+        && bbox[1] >= -90f64
+        && bbox[3] <= 90f64
+        && (epsg == 4326
+            || epsg == 4322
+            || epsg == 4629
+            || epsg == 4277
+            || (!wkt_lower.contains("projcs[") && !wkt_lower.contains("not specified")));
+    let footprint = Crs::from_definition(epsg, &wkt, geographic).map(|crs| {
+        let corners = [
+            (bbox[0], bbox[1]),
+            (bbox[2], bbox[1]),
+            (bbox[2], bbox[3]),
+            (bbox[0], bbox[3]),
+            (bbox[0], bbox[1]),
+        ];
+        corners
+            .iter()
+            .map(|&(x, y)| {
+                let (lon, lat) = crs.to_lon_lat(x, y);
+                [lon, lat]
+            })
+            .collect()
+    });
+    Ok(Some(FileExtent {
+        bbox,
+        epsg,
+        shape,
+        transform,
+        footprint,
+    }))
+}
+
+/// Parses a bounding box given as 'west,south,east,north'.
+pub(super) fn parse_bbox(value: &str) -> Result<[f64; 4], Error> {
+    let values: Vec<f64> = value
+        .split(',')
+        .map(|v| v.trim().parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()
+        .map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("The bounding box '{}' could not be read; it must be given as west,south,east,north.", value),
+            )
+        })?;
+    if values.len() != 4 || values[0] > values[2] || values[1] > values[3] {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("The bounding box '{}' must be given as west,south,east,north.", value),
+        ));
+    }
+    Ok([values[0], values[1], values[2], values[3]])
+}
+
+/// Returns the media type of a file, by its extension, for use in STAC assets.
+pub(super) fn media_type(file_name: &str) -> Option<&'static str> {
+    match extension(file_name).as_str() {
+        "tif" | "tiff" | "gtif" | "gtiff" => Some("image/tiff; application=geotiff"),
+        "json" | "geojson" => Some("application/geo+json"),
+        "las" => Some("application/vnd.las"),
+        "laz" => Some("application/vnd.laszip"),
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "html" => Some("text/html"),
+        "csv" => Some("text/csv"),
+        "txt" => Some("text/plain"),
+        "shp" => Some("application/vnd.shp"),
+        _ => None,
+    }
+}
+
+/// Returns the href of a file relative to a directory, using forward slashes, if the file is
+/// within the directory, and the file name unchanged otherwise.
+pub(super) fn relative_href(file_name: &str, directory: &Path) -> String {
+    match Path::new(file_name).strip_prefix(directory) {
+        Ok(p) if !p.components().any(|c| c == Component::ParentDir) => {
+            format!("./{}", p.to_string_lossy().replace('\\', "/"))
+        }
+        _ => file_name.to_string(),
+    }
+}
@@ -0,0 +1,494 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Requests are made in the same way as for object stores, i.e. with the curl program, which is
+installed by default on Windows 10 and later, macOS and most Linux distributions, unless built with
+the http feature, in which case a built-in HTTP client is used.
+*/
+
+use super::stac::*;
+use crate::tools::*;
+use serde_json::{json, Value};
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+use whitebox_common::utils::{download_object, post_json, read_input};
+use whitebox_common::errors::ErrorCause;
+
+/// This tool searches a STAC (SpatioTemporal Asset Catalog) API, such as those of Microsoft's
+/// Planetary Computer or Element 84's Earth Search, for items (e.g. satellite scenes or DEM tiles)
+/// and downloads their assets, so that they may be used as the inputs of other tools. The search
+/// is made against the API's `/search` endpoint (`--url` is the root of the API) and may be
+/// restricted to one or more collections (`--collections`, a comma-separated list of collection
+/// IDs), to a bounding box (`--bbox`), and to a date and time or interval (`--datetime`, an RFC 3339
+/// date-time or an interval such as `2023-06-01/2023-08-31`, where `..` denotes an open end). The
+/// bounding box is given either as `west,south,east,north` in geographic coordinates (WGS84), or as
+/// the name of a raster or vector file, whose extent is used. The first `--max_items` matching
+/// items (default 10) are retrieved, following the API's pages of results as needed.
+///
+/// The assets of each item that are downloaded are named by `--assets`, a comma-separated list of
+/// asset keys (e.g. `B04,B08` or `data`). By default, the assets with the `data` role are
+/// downloaded, or all of the assets of items that do not use roles. Assets are saved in the output
+/// directory (`--outdir`, the working directory by default) as ITEM_ASSET.EXT, e.g.
+/// `S2B_10TES_20230715_0_L2A_B04.tif`. Files that already exist are not downloaded again, so that
+/// an interrupted search may be resumed.
+///
+/// The matching items are written to the output file (`--output`) as a STAC ItemCollection, i.e. a
+/// GeoJSON FeatureCollection, in which the hrefs of the downloaded assets refer to the local files,
+/// with the original locations retained as the `remote` alternate of each asset. The list of
+/// downloaded files is also printed, separated by semicolons, in the form accepted by tools with
+/// multiple inputs. If `--list_only` is specified, nothing is downloaded and the item collection
+/// refers only to the remote assets.
+///
+/// Requests are made using the `curl` program, which must be available on the system path, unless
+/// WhiteboxTools was built with the `http` feature. Assets in cloud object stores, i.e. those with `s3://`, `gs://` or `az://` hrefs, are downloaded using
+/// the credentials in the environment (e.g. `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`), in
+/// the same way as other tools read such URIs. Assets with other hrefs are skipped with a warning,
+/// as are assets whose hrefs require signing (e.g. by the Planetary Computer's SAS token service).
+///
+/// # See Also
+/// `ExportStacItems`
+pub struct StacSearch {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl StacSearch {
+    pub fn new() -> StacSearch {
+        // public constructor
+        let name = "StacSearch".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Searches a STAC API for items by collection, bounding box and date, and downloads their assets."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "STAC API URL".to_owned(),
+            flags: vec!["--url".to_owned()],
+            description: "Root URL of the STAC API, e.g. https://earth-search.aws.element84.com/v1.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Collections".to_owned(),
+            flags: vec!["--collections".to_owned()],
+            description: "Comma-separated list of collection IDs to search (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Bounding Box".to_owned(),
+            flags: vec!["--bbox".to_owned()],
+            description: "Bounding box as west,south,east,north in geographic coordinates, or a raster or vector file whose extent is used (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Date and Time".to_owned(),
+            flags: vec!["--datetime".to_owned()],
+            description: "RFC 3339 date-time or interval, e.g. 2023-06-01/2023-08-31 (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Asset Keys".to_owned(),
+            flags: vec!["--assets".to_owned()],
+            description: "Comma-separated list of the keys of the assets to download (optional; defaults to the data assets).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Number of Items".to_owned(),
+            flags: vec!["--max_items".to_owned()],
+            description: "Maximum number of items to retrieve.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Directory".to_owned(),
+            flags: vec!["--outdir".to_owned()],
+            description: "Output directory for the downloaded assets (optional; defaults to the working directory).".to_owned(),
+            parameter_type: ParameterType::Directory,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Item Collection File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output STAC ItemCollection (GeoJSON) file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "List items without downloading?".to_owned(),
+            flags: vec!["--list_only".to_owned()],
+            description: "Write the matching items without downloading their assets.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --url=https://earth-search.aws.element84.com/v1 --collections=sentinel-2-l2a --bbox=-80.6,43.4,-80.1,43.7 --datetime=2023-07-01/2023-07-31 --assets=red,nir -o=scenes.json
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --url=https://earth-search.aws.element84.com/v1 --collections=cop-dem-glo-30 --bbox=dem.tif -o=tiles.json --list_only",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        StacSearch {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for StacSearch {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let url = parsed.get_string("url").unwrap_or_default();
+        let collections = parsed.get_string("collections").unwrap_or_default();
+        let bbox_value = parsed.get_string("bbox").unwrap_or_default();
+        let datetime = parsed.get_string("datetime").unwrap_or_default();
+        let asset_keys = parsed.get_string("assets").unwrap_or_default();
+        let max_items = parsed.get_usize("max_items")?.unwrap_or(10);
+        let mut output_directory = parsed.get_string("outdir").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let list_only = parsed.get_bool("list_only");
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let url = url.trim().trim_end_matches('/').to_string();
+        if url.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The URL of the STAC API (--url) must be specified.",
+            ));
+        }
+        if max_items == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum number of items (--max_items) must be at least 1.",
+            ));
+        }
+        output_file = resolve_path(&output_file, working_directory);
+        if output_directory.trim().is_empty() {
+            output_directory = working_directory.to_string();
+        } else {
+            output_directory = resolve_path(&output_directory, working_directory);
+        }
+        if !list_only && !output_directory.is_empty() {
+            fs::create_dir_all(&output_directory)?;
+        }
+
+        let start = Instant::now();
+
+        // Build the search request.
+        let mut body = json!({ "limit": max_items.min(100) });
+        let collections: Vec<String> = split_list(&collections);
+        if !collections.is_empty() {
+            body["collections"] = json!(collections);
+        }
+        if !bbox_value.trim().is_empty() {
+            body["bbox"] = json!(search_bbox(&bbox_value, working_directory)?);
+        }
+        if !datetime.trim().is_empty() {
+            body["datetime"] = json!(datetime.trim());
+        }
+
+        if verbose {
+            println!("Searching {}...", url);
+        }
+        let mut items: Vec<Value> = vec![];
+        let mut request = Some((format!("{}/search", url), Some(body)));
+        while let Some((page_url, page_body)) = request.take() {
+            reporter.check_cancelled()?;
+            let page = match &page_body {
+                Some(b) => post_json(&page_url, &b.to_string())?,
+                None => read_input(&page_url)?,
+            };
+            let response: Value = serde_json::from_slice(&page)
+                .map_err(|e| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("The response of {} is not valid JSON: {}", page_url, e),
+                    )
+                })?;
+            let features = match response["features"].as_array() {
+                Some(f) => f,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("The response of {} is not a STAC ItemCollection.", page_url),
+                    ))
+                }
+            };
+            for feature in features {
+                if items.len() < max_items {
+                    items.push(feature.clone());
+                }
+            }
+            if items.len() < max_items && !features.is_empty() {
+                request = next_page(&response, page_body);
+            }
+        }
+        if verbose {
+            println!("Found {} matching item(s).", items.len());
+        }
+
+        // Download the selected assets.
+        let asset_keys = split_list(&asset_keys);
+        let mut downloaded: Vec<String> = vec![];
+        if !list_only {
+            let directory = path::Path::new(&output_directory);
+            let num_items = items.len();
+            for (n, item) in items.iter_mut().enumerate() {
+                let item_id = sanitize(item["id"].as_str().unwrap_or("item"));
+                let keys = selected_assets(item, &asset_keys);
+                for key in keys {
+                    reporter.check_cancelled()?;
+                    let asset = &mut item["assets"][&key];
+                    let href = asset["href"].as_str().unwrap_or("").to_string();
+                    let in_object_store = ["s3://", "gs://", "az://"]
+                        .iter()
+                        .any(|scheme| href.starts_with(scheme));
+                    if !href.starts_with("http://") && !href.starts_with("https://") && !in_object_store {
+                        if verbose {
+                            println!(
+                                "Warning: asset '{}' of item {} is not accessible over HTTP(S) or from an object store ({}) and was skipped.",
+                                key, item_id, href
+                            );
+                        }
+                        continue;
+                    }
+                    let ext = extension(&href);
+                    let file_name = if ext.is_empty() {
+                        format!("{}_{}", item_id, sanitize(&key))
+                    } else {
+                        format!("{}_{}.{}", item_id, sanitize(&key), ext)
+                    };
+                    let local_file = directory.join(&file_name).to_string_lossy().to_string();
+                    if !path::Path::new(&local_file).exists() {
+                        if verbose {
+                            println!("Downloading {}...", file_name);
+                        }
+                        // download to a temporary file so that an interrupted download is not
+                        // mistaken for a complete one
+                        let partial_file = format!("{}.part", local_file);
+                        match download_object(&href, &partial_file) {
+                            Ok(_) => fs::rename(&partial_file, &local_file)?,
+                            Err(e) => {
+                                let _ = fs::remove_file(&partial_file);
+                                if verbose {
+                                    println!("Warning: asset '{}' of item {} was not downloaded. {}", key, item_id, e);
+                                }
+                                continue;
+                            }
+                        }
+                    }
+                    asset["alternate"] = json!({ "remote": { "href": href } });
+                    asset["href"] = json!(local_file);
+                    downloaded.push(local_file);
+                }
+                if verbose {
+                    let progress = (100.0_f64 * (n + 1) as f64 / num_items as f64) as usize;
+                    reporter.progress("Downloading assets", progress);
+                }
+            }
+        }
+
+        let collection = json!({
+            "type": "FeatureCollection",
+            "features": items,
+            "links": [ { "rel": "root", "href": url, "type": "application/json" } ],
+        });
+        fs::write(&output_file, serde_json::to_string_pretty(&collection)?)?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            if !downloaded.is_empty() {
+                println!("Downloaded files:\n{}", downloaded.join(";"));
+            }
+            println!("Output file written");
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits a comma-separated list, ignoring empty entries.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Replaces the characters of an ID that are not suitable for a file name.
+fn sanitize(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' })
+        .collect()
+}
+
+/// Returns the geographic bounding box of a search, given either as coordinates or as the name
+/// of a raster or vector file.
+fn search_bbox(value: &str, working_directory: &str) -> Result<[f64; 4], Error> {
+    if value.split(',').count() == 4 {
+        return parse_bbox(value);
+    }
+    let file_name = resolve_input_path(value.trim(), working_directory)?;
+    if !is_raster_file(&file_name) && !is_vector_file(&file_name) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The bounding box (--bbox) must be given as west,south,east,north, or as a raster or vector file.",
+        ));
+    }
+    match read_extent(&file_name)?.and_then(|e| e.geographic_bbox()) {
+        Some(bbox) => Ok(bbox),
+        None => Err(ErrorCause::UnsupportedFormat.error(
+            format!(
+                "The coordinate reference system of {} is not supported; it must be geographic, Web Mercator, or a WGS84 or NAD83 UTM zone.",
+                value
+            ),
+        )),
+    }
+}
+
+/// Returns the keys of the assets of an item that are to be downloaded.
+fn selected_assets(item: &Value, asset_keys: &[String]) -> Vec<String> {
+    let assets = match item["assets"].as_object() {
+        Some(a) => a,
+        None => return vec![],
+    };
+    if !asset_keys.is_empty() {
+        return asset_keys
+            .iter()
+            .filter(|k| assets.contains_key(k.as_str()))
+            .cloned()
+            .collect();
+    }
+    let uses_roles = assets.values().any(|a| a["roles"].is_array());
+    assets
+        .iter()
+        .filter(|(_, a)| {
+            !uses_roles
+                || a["roles"]
+                    .as_array()
+                    .map(|roles| roles.iter().any(|r| r == "data"))
+                    .unwrap_or(false)
+        })
+        .map(|(k, _)| k.clone())
+        .collect()
+}
+
+/// Returns the request for the next page of search results, from the `next` link of a response.
+/// A POST link may carry a body of its own, which replaces the previous body or, if `merge` is
+/// true, is merged into it.
+fn next_page(response: &Value, body: Option<Value>) -> Option<(String, Option<Value>)> {
+    let link = response["links"]
+        .as_array()?
+        .iter()
+        .find(|l| l["rel"] == "next")?;
+    let href = link["href"].as_str()?.to_string();
+    if link["method"].as_str().unwrap_or("GET").to_uppercase() != "POST" {
+        return Some((href, None));
+    }
+    let mut next_body = body.unwrap_or(json!({}));
+    match link["body"].as_object() {
+        Some(link_body) if link["merge"].as_bool().unwrap_or(false) => {
+            for (k, v) in link_body {
+                next_body[k] = v.clone();
+            }
+        }
+        Some(_) => next_body = link["body"].clone(),
+        None => {}
+    }
+    Some((href, Some(next_body)))
+}
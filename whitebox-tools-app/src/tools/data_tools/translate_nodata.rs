@@ -0,0 +1,364 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool rewrites the nodata value of a raster and can be used to mask additional values, or ranges of
+/// values, by converting them to nodata. It is useful for cleaning up data sets in which missing data are
+/// represented in several different ways, e.g. a DEM that uses both -9999 and -32768 to denote voids, or an
+/// image in which fill values (e.g. 0 or 255) should be excluded from analysis.
+///
+/// Cells in the input raster (`--input`) that are nodata, including NaN values and values that match the
+/// nodata value to within the precision of a 32-bit float, are assigned the new nodata value
+/// (`--new_nodata`) in the output raster (`--output`). If no new nodata value is specified, the input nodata
+/// value is retained. Additionally, any cells containing one of the values in the optional list
+/// `--values` (separated by commas or semicolons) are set to nodata, as are any cells with values
+/// falling within one of the optional inclusive ranges specified by `--ranges`. Ranges are specified as
+/// `min:max` pairs separated by semicolons; either bound may be omitted to create an open-ended range,
+/// e.g. `--ranges=":-1000;250:"` masks all values less than or equal to -1000 and greater than or equal to
+/// 250. The tool reports the number of cells that were converted to nodata.
+///
+/// Notice that valid cells in the input that happen to equal the new nodata value will become nodata in the
+/// output. The tool issues a warning if this occurs.
+///
+/// # See Also
+/// `SetNodataValue`, `ModifyNoDataValue`, `ConvertNodataToZero`, `IsNoData`
+pub struct TranslateNodata {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TranslateNodata {
+    pub fn new() -> TranslateNodata {
+        // public constructor
+        let name = "TranslateNodata".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description =
+            "Rewrites the nodata value of a raster and masks specified values and value ranges as nodata."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "New NoData Value (optional)".to_owned(),
+            flags: vec!["--new_nodata".to_owned()],
+            description: "New nodata value; by default, the input nodata value is retained.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Values to Mask (optional)".to_owned(),
+            flags: vec!["--values".to_owned()],
+            description: "Values to convert to nodata, separated by commas or semicolons.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Value Ranges to Mask (optional)".to_owned(),
+            flags: vec!["--ranges".to_owned()],
+            description: "Inclusive value ranges to convert to nodata, e.g. ':-1000;250:'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=in.tif -o=out.tif --new_nodata=-32768 --values=\"-9999,0\" --ranges=\":-1000\"", short_exe, name).replace("*", &sep);
+
+        TranslateNodata {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TranslateNodata {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut new_nodata = parsed.get_f64("new_nodata").unwrap_or(f64::NAN);
+        let values_str = parsed.get_string("values").unwrap_or_default();
+        let ranges_str = parsed.get_string("ranges").unwrap_or_default();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        let mut mask_values = vec![];
+        for v in values_str
+            .split(|c| c == ',' || c == ';')
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+        {
+            match v.parse::<f64>() {
+                Ok(val) => mask_values.push(val),
+                Err(_) => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Error parsing the mask value '{}'.", v),
+                    ))
+                }
+            }
+        }
+        let mut mask_ranges = vec![];
+        for r in ranges_str
+            .split(";")
+            .map(|r| r.trim())
+            .filter(|r| !r.is_empty())
+        {
+            let bounds: Vec<&str> = r.split(":").collect();
+            if bounds.len() != 2 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Invalid range '{}'; ranges must be specified as 'min:max'.", r),
+                ));
+            }
+            let parse_bound = |b: &str, default: f64| -> Result<f64, Error> {
+                if b.trim().is_empty() {
+                    Ok(default)
+                } else {
+                    b.trim().parse::<f64>().map_err(|_| {
+                        Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Error parsing the range bound '{}'.", b),
+                        )
+                    })
+                }
+            };
+            let min = parse_bound(bounds[0], f64::NEG_INFINITY)?;
+            let max = parse_bound(bounds[1], f64::INFINITY)?;
+            if min > max {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Invalid range '{}'; the minimum is greater than the maximum.", r),
+                ));
+            }
+            mask_ranges.push((min, max));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+        let mut progress: i32;
+        let mut old_progress: i32 = -1;
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        if new_nodata.is_nan() {
+            new_nodata = nodata;
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.nodata = new_nodata;
+
+        let mask_values = Arc::new(mask_values);
+        let mask_ranges = Arc::new(mask_ranges);
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let mask_values = mask_values.clone();
+            let mask_ranges = mask_ranges.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let mut z: f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![new_nodata; columns as usize];
+                    let mut num_masked = 0usize;
+                    let mut num_collisions = 0usize;
+                    for col in 0..columns {
+                        z = input.get_value(row, col);
+                        if is_nodata(z, nodata) {
+                            continue;
+                        }
+                        if mask_values.iter().any(|&v| z == v)
+                            || mask_ranges.iter().any(|&(min, max)| z >= min && z <= max)
+                        {
+                            num_masked += 1;
+                            continue;
+                        }
+                        if is_nodata(z, new_nodata) {
+                            num_collisions += 1;
+                        }
+                        data[col as usize] = z;
+                    }
+                    if tx.send((row, data, num_masked, num_collisions)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut num_masked = 0usize;
+        let mut num_collisions = 0usize;
+        for r in 0..rows {
+            let (row, data, masked, collisions) =
+                rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+            num_masked += masked;
+            num_collisions += collisions;
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as i32;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress as usize);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Number of cells converted to nodata: {}", num_masked);
+        }
+        if num_collisions > 0 {
+            println!(
+                "Warning: {} valid cells in the input are equal to the new nodata value and will be treated as nodata.",
+                num_collisions
+            );
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input raster file: {}", input_file));
+        output.add_metadata_entry(format!("New nodata value: {}", new_nodata));
+        if !values_str.is_empty() {
+            output.add_metadata_entry(format!("Masked values: {}", values_str));
+        }
+        if !ranges_str.is_empty() {
+            output.add_metadata_entry(format!("Masked ranges: {}", ranges_str));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
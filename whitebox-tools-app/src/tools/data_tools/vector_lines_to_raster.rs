@@ -94,7 +94,7 @@ impl VectorLinesToRaster {
                 "Background value to set to NoData. Without this flag, it will be set to 0.0."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true,
         });
 
@@ -175,72 +175,19 @@ impl WhiteboxTool for VectorLinesToRaster {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut field_name = String::from("FID");
-        let mut output_file = String::new();
-        let mut cell_size = 0f64;
-        let mut base_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut field_name = parsed.get_string("field").unwrap_or(String::from("FID"));
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let cell_size = parsed.get_f64("cell_size").unwrap_or(0f64);
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
         let nodata = -32768.0f64;
         let mut background_val = 0f64;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                field_name = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-cell_size" {
-                cell_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-base" {
-                base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-nodata" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    background_val = nodata;
-                }
-            }
+        if parsed.get_bool("nodata") {
+            background_val = nodata;
         }
 
         if verbose {
@@ -254,17 +201,11 @@ impl WhiteboxTool for VectorLinesToRaster {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -310,9 +251,7 @@ impl WhiteboxTool for VectorLinesToRaster {
         // If both are specified, the base raster takes priority.
 
         let mut output = if !base_file.trim().is_empty() || cell_size == 0f64 {
-            if !base_file.contains(&sep) && !base_file.contains("/") {
-                base_file = format!("{}{}", working_directory, base_file);
-            }
+            base_file = resolve_input_path(&base_file, working_directory)?;
             let base = Raster::new(&base_file, "r")?;
             Raster::initialize_using_file(&output_file, &base)
         } else {
@@ -339,12 +278,12 @@ impl WhiteboxTool for VectorLinesToRaster {
             configs.nodata = nodata;
             configs.data_type = DataType::F32;
             configs.photometric_interp = PhotometricInterpretation::Continuous;
-            configs.projection = vector_data.projection.clone();
+            configs.set_crs(&whitebox_common::spatial_ref_system::CoordinateReferenceSystem::from_wkt(&vector_data.projection));
 
             Raster::initialize_using_config(&output_file, &configs)
         };
 
-        if background_val != nodata {
+        if !is_nodata(background_val, nodata) {
             output.reinitialize_values(background_val);
         }
 
@@ -377,11 +316,12 @@ impl WhiteboxTool for VectorLinesToRaster {
                 attribute_data[record_num] = (record_num + 1) as f64;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (vector_data.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Reading attributes: {}%", progress);
+                    reporter.progress("Reading attributes", progress);
                     old_progress = progress;
                 }
             }
@@ -516,15 +456,11 @@ impl WhiteboxTool for VectorLinesToRaster {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                 if progress != old_progress {
-                    println!(
-                        "Rasterizing {} of {}: {}%",
-                        record_num + 1,
-                        num_records,
-                        progress
-                    );
+                    reporter.progress(&format!("Rasterizing {} of {}", record_num + 1, num_records), progress);
                     old_progress = progress;
                 }
             }
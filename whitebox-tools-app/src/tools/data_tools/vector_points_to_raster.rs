@@ -98,7 +98,7 @@ impl VectorPointsToRaster {
                 "Background value to set to NoData. Without this flag, it will be set to 0.0."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true,
         });
 
@@ -179,79 +179,23 @@ impl WhiteboxTool for VectorPointsToRaster {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut field_name = String::from("FID");
-        let mut output_file = String::new();
-        let mut cell_size = 0f64;
-        let mut base_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut field_name = parsed.get_string("field").unwrap_or(String::from("FID"));
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let cell_size = parsed.get_f64("cell_size").unwrap_or(0f64);
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
         let nodata = -32768.0f64;
         let mut background_val = 0f64;
         let mut assign_op = String::from("last");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if parsed.get_bool("nodata") {
+            background_val = nodata;
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                field_name = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-cell_size" {
-                cell_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-base" {
-                base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-nodata" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    background_val = nodata;
-                }
-            } else if flag_val == "-assign" {
-                assign_op = if keyval {
-                    vec[1].to_lowercase()
-                } else {
-                    args[i + 1].to_lowercase()
-                };
-            }
+        if let Some(value) = parsed.get_string("assign") {
+            assign_op = value.to_lowercase();
         }
 
         if verbose {
@@ -265,17 +209,11 @@ impl WhiteboxTool for VectorPointsToRaster {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -319,9 +257,7 @@ impl WhiteboxTool for VectorPointsToRaster {
         // If both are specified, the base raster takes priority.
 
         let mut output = if !base_file.trim().is_empty() || cell_size == 0f64 {
-            if !base_file.contains(&sep) && !base_file.contains("/") {
-                base_file = format!("{}{}", working_directory, base_file);
-            }
+            base_file = resolve_input_path(&base_file, working_directory)?;
             let base = Raster::new(&base_file, "r")?;
             Raster::initialize_using_file(&output_file, &base)
         } else {
@@ -348,13 +284,12 @@ impl WhiteboxTool for VectorPointsToRaster {
             configs.nodata = nodata;
             configs.data_type = DataType::F32;
             configs.photometric_interp = PhotometricInterpretation::Continuous;
-            // configs.epsg_code = vector_data.projection.clone();
-            configs.projection = vector_data.projection.clone();
+            configs.set_crs(&whitebox_common::spatial_ref_system::CoordinateReferenceSystem::from_wkt(&vector_data.projection));
 
             Raster::initialize_using_config(&output_file, &configs)
         };
 
-        if background_val != nodata {
+        if !is_nodata(background_val, nodata) {
             output.reinitialize_values(background_val);
         }
 
@@ -385,11 +320,12 @@ impl WhiteboxTool for VectorPointsToRaster {
                 attribute_data[record_num] = (record_num + 1) as f64;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (vector_data.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Reading attributes: {}%", progress);
+                    reporter.progress("Reading attributes", progress);
                     old_progress = progress;
                 }
             }
@@ -409,15 +345,11 @@ impl WhiteboxTool for VectorPointsToRaster {
                     col = output.get_column_from_x(x);
                     output.set_value(row, col, attribute_data[record_num]);
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Rasterizing {} of {}: {}%",
-                            record_num + 1,
-                            num_records,
-                            progress
-                        );
+                        reporter.progress(&format!("Rasterizing {} of {}", record_num + 1, num_records), progress);
                         old_progress = progress;
                     }
                 }
@@ -431,19 +363,15 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata {
+                    if z == background_val || is_nodata(z, nodata) {
                         output.set_value(row, col, attribute_data[record_num]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Rasterizing {} of {}: {}%",
-                            record_num + 1,
-                            num_records,
-                            progress
-                        );
+                        reporter.progress(&format!("Rasterizing {} of {}", record_num + 1, num_records), progress);
                         old_progress = progress;
                     }
                 }
@@ -457,19 +385,15 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata || attribute_data[record_num] < z {
+                    if z == background_val || is_nodata(z, nodata) || attribute_data[record_num] < z {
                         output.set_value(row, col, attribute_data[record_num]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Rasterizing {} of {}: {}%",
-                            record_num + 1,
-                            num_records,
-                            progress
-                        );
+                        reporter.progress(&format!("Rasterizing {} of {}", record_num + 1, num_records), progress);
                         old_progress = progress;
                     }
                 }
@@ -483,19 +407,15 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata || attribute_data[record_num] > z {
+                    if z == background_val || is_nodata(z, nodata) || attribute_data[record_num] > z {
                         output.set_value(row, col, attribute_data[record_num]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Rasterizing {} of {}: {}%",
-                            record_num + 1,
-                            num_records,
-                            progress
-                        );
+                        reporter.progress(&format!("Rasterizing {} of {}", record_num + 1, num_records), progress);
                         old_progress = progress;
                     }
                 }
@@ -509,21 +429,17 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata {
+                    if z == background_val || is_nodata(z, nodata) {
                         output.set_value(row, col, attribute_data[record_num]);
                     } else {
                         output.set_value(row, col, z + attribute_data[record_num]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Rasterizing {} of {}: {}%",
-                            record_num + 1,
-                            num_records,
-                            progress
-                        );
+                        reporter.progress(&format!("Rasterizing {} of {}", record_num + 1, num_records), progress);
                         old_progress = progress;
                     }
                 }
@@ -537,21 +453,17 @@ impl WhiteboxTool for VectorPointsToRaster {
                     row = output.get_row_from_y(y);
                     col = output.get_column_from_x(x);
                     z = output.get_value(row, col);
-                    if z == background_val || z == nodata {
+                    if z == background_val || is_nodata(z, nodata) {
                         output.set_value(row, col, 1f64);
                     } else {
                         output.set_value(row, col, z + 1f64);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Rasterizing {} of {}: {}%",
-                            record_num + 1,
-                            num_records,
-                            progress
-                        );
+                        reporter.progress(&format!("Rasterizing {} of {}", record_num + 1, num_records), progress);
                         old_progress = progress;
                     }
                 }
@@ -0,0 +1,562 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::spatial_ref_system::CoordinateReferenceSystem;
+use whitebox_common::structures::{DistanceMetric, FixedRadiusSearch2D};
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool grids the points contained within a delimited text file (`--input`), such as the XYZ and
+/// CSV files commonly exchanged with surveying and bathymetric software, directly to a raster (`--output`).
+/// The fields (columns) containing the x, y, and z values are specified using zero-based field numbers
+/// (`--xfield`, `--yfield`, and `--zfield`), which default to the first three fields. The field delimiter
+/// (comma, tab, semicolon, or whitespace) is detected automatically, lines beginning with '#' are ignored,
+/// and a header line is detected, and skipped, if its x, y, or z values are not numeric.
+///
+/// The output grid may be based on an existing raster (`--base`) or else a grid resolution (`--cell_size`),
+/// in which case the grid is aligned such that the cell centres of a regularly spaced input coincide with
+/// the point positions. The interpolation method (`--method`) may be one of the following:
+///
+/// | Method  | Description |
+/// |---------|-------------|
+/// | nearest | The value of the nearest point within the search radius |
+/// | idw     | Inverse-distance weighted average of the points within the search radius, using the `--weight` exponent |
+/// | mean    | The average value of the points falling within each cell |
+/// | min     | The minimum value of the points falling within each cell |
+/// | max     | The maximum value of the points falling within each cell |
+///
+/// The search radius (`--radius`) used by the nearest and idw methods defaults to twice the grid
+/// resolution; cells without points within the search radius (or, for the binning methods, containing no
+/// points) are assigned nodata. The coordinate reference system of the output may optionally be set using
+/// an EPSG code (`--epsg`).
+///
+/// # See Also
+/// `RasterToXyz`, `CsvPointsToVector`, `NearestNeighbourGridding`, `IdwInterpolation`
+pub struct XyzToRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl XyzToRaster {
+    pub fn new() -> XyzToRaster {
+        // public constructor
+        let name = "XyzToRaster".to_string();
+        let toolbox = "Data Tools".to_string();
+        let description = "Grids the points in a delimited XYZ/CSV text file to a raster.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input XYZ/CSV File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input delimited text file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Text),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Raster File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "X Field Number (zero-based)".to_owned(),
+            flags: vec!["--xfield".to_owned()],
+            description: "X field number (e.g. 0 for first field).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Y Field Number (zero-based)".to_owned(),
+            flags: vec!["--yfield".to_owned()],
+            description: "Y field number (e.g. 1 for second field).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Z Field Number (zero-based)".to_owned(),
+            flags: vec!["--zfield".to_owned()],
+            description: "Z field number (e.g. 2 for third field).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Interpolation Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Interpolation method; options include 'nearest', 'idw', 'mean', 'min', and 'max'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "nearest".to_owned(),
+                "idw".to_owned(),
+                "mean".to_owned(),
+                "min".to_owned(),
+                "max".to_owned(),
+            ]),
+            default_value: Some("nearest".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Search Radius (optional)".to_owned(),
+            flags: vec!["--radius".to_owned()],
+            description: "Search radius used by the nearest and idw methods; defaults to twice the cell size.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "IDW Weight (Exponent) Value".to_owned(),
+            flags: vec!["--weight".to_owned()],
+            description: "IDW weight value.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Optionally specified cell size of output raster. Not used when base raster is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optionally specified input base raster file. Not used when a cell size is specified.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "EPSG Projection".to_owned(),
+            flags: vec!["--epsg".to_owned()],
+            description: "EPSG projection (e.g. 2958).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=soundings.xyz -o=bathymetry.tif --method=idw --cell_size=5.0 --epsg=32617",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        XyzToRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for XyzToRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+        let x_field = parsed.get_usize("xfield")?.unwrap_or(0);
+        let y_field = parsed.get_usize("yfield")?.unwrap_or(1);
+        let z_field = parsed.get_usize("zfield")?.unwrap_or(2);
+        let method = parsed.get_string("method").unwrap_or(String::from("nearest")).to_lowercase();
+        let mut radius = parsed.get_f64("radius").unwrap_or(0f64);
+        let weight = parsed.get_f64("weight").unwrap_or(2f64);
+        let grid_res = parsed.get_f64("cell_size").unwrap_or(0f64);
+        let epsg = parsed.get_i64("epsg").map(|v| v as u16).unwrap_or(0u16);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_path(&input_file, working_directory);
+        output_file = resolve_path(&output_file, working_directory);
+
+        if method != "nearest" && method != "idw" && method != "mean" && method != "min" && method != "max" {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unrecognized interpolation method; options include 'nearest', 'idw', 'mean', 'min', and 'max'.",
+            ));
+        }
+        if base_file.trim().is_empty() && grid_res <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either a base raster or a positive cell size must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let start = Instant::now();
+
+        let points = read_xyz_file(&input_file, x_field, y_field, z_field)?;
+        if points.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input file does not contain any valid points.",
+            ));
+        }
+        if verbose {
+            println!("Number of points: {}", points.len());
+        }
+
+        let nodata = -32768.0f64;
+        let mut output = if !base_file.trim().is_empty() {
+            base_file = resolve_input_path(&base_file, working_directory)?;
+            let mut base = Raster::new(&base_file, "r")?;
+            base.configs.nodata = nodata;
+            Raster::initialize_using_file(&output_file, &base)
+        } else {
+            // The grid is aligned such that the cell centres of regularly spaced points coincide
+            // with the point positions.
+            let mut min_x = f64::INFINITY;
+            let mut max_x = f64::NEG_INFINITY;
+            let mut min_y = f64::INFINITY;
+            let mut max_y = f64::NEG_INFINITY;
+            for p in &points {
+                reporter.check_cancelled()?;
+                min_x = min_x.min(p.0);
+                max_x = max_x.max(p.0);
+                min_y = min_y.min(p.1);
+                max_y = max_y.max(p.1);
+            }
+            let west = min_x - grid_res / 2f64;
+            let north = max_y + grid_res / 2f64;
+            let rows = ((north - (min_y - grid_res / 2f64)) / grid_res).round().max(1f64) as usize;
+            let columns = (((max_x + grid_res / 2f64) - west) / grid_res).round().max(1f64) as usize;
+
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = rows;
+            configs.columns = columns;
+            configs.north = north;
+            configs.south = north - rows as f64 * grid_res;
+            configs.east = west + columns as f64 * grid_res;
+            configs.west = west;
+            configs.resolution_x = grid_res;
+            configs.resolution_y = grid_res;
+            configs.nodata = nodata;
+            configs.data_type = DataType::F32;
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            Raster::initialize_using_config(&output_file, &configs)
+        };
+        output.configs.nodata = nodata; // in case a base image is used with a different nodata value.
+        output.reinitialize_values(nodata);
+        if epsg > 0 {
+            output.configs.set_crs(&CoordinateReferenceSystem::from_epsg(epsg));
+        }
+
+        let rows = output.configs.rows as isize;
+        let columns = output.configs.columns as isize;
+        let west = output.configs.west;
+        let north = output.configs.north;
+        let res_x = output.configs.resolution_x;
+        let res_y = output.configs.resolution_y;
+
+        if method == "nearest" || method == "idw" {
+            if radius <= 0f64 {
+                radius = 2f64 * res_x.max(res_y);
+            }
+            let mut frs: FixedRadiusSearch2D<f64> =
+                FixedRadiusSearch2D::new(radius, DistanceMetric::Euclidean);
+            for p in &points {
+                frs.insert(p.0, p.1, p.2);
+            }
+            let frs = Arc::new(frs);
+            let use_idw = method == "idw";
+            let configs = whitebox_common::configs::get_configs()?;
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let frs = frs.clone();
+                let tx = tx.clone();
+                spawn(move || {
+                    let (mut x, mut y): (f64, f64);
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        let mut data = vec![nodata; columns as usize];
+                        for col in 0..columns {
+                            x = west + (col as f64 + 0.5) * res_x;
+                            y = north - (row as f64 + 0.5) * res_y;
+                            if !use_idw {
+                                let ret = frs.knn_search(x, y, 1);
+                                if ret.len() == 1 && ret[0].1 <= radius {
+                                    data[col as usize] = ret[0].0;
+                                }
+                            } else {
+                                let ret = frs.search(x, y);
+                                let mut sum_weights = 0f64;
+                                let mut sum = 0f64;
+                                for &(z, dist) in &ret {
+                                    if dist == 0f64 {
+                                        // a point coincides with the cell centre
+                                        sum_weights = 1f64;
+                                        sum = z;
+                                        break;
+                                    }
+                                    let w = 1f64 / dist.powf(weight);
+                                    sum_weights += w;
+                                    sum += w * z;
+                                }
+                                if sum_weights > 0f64 {
+                                    data[col as usize] = sum / sum_weights;
+                                }
+                            }
+                        }
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+
+            for row in 0..rows {
+                let (r, data) = rx.recv().expect("Error receiving data from thread.");
+                output.set_row_data(r, data);
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Progress", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        } else {
+            // binning methods
+            let mut counts = vec![0usize; (rows * columns) as usize];
+            let num_points = points.len();
+            for (i, p) in points.iter().enumerate() {
+                let row = output.get_row_from_y(p.1);
+                let col = output.get_column_from_x(p.0);
+                if row >= 0 && row < rows && col >= 0 && col < columns {
+                    let idx = (row * columns + col) as usize;
+                    let z = output.get_value(row, col);
+                    if counts[idx] == 0 {
+                        output.set_value(row, col, p.2);
+                    } else if method == "mean" {
+                        output.set_value(row, col, z + p.2);
+                    } else if method == "min" {
+                        if p.2 < z {
+                            output.set_value(row, col, p.2);
+                        }
+                    } else if p.2 > z {
+                        output.set_value(row, col, p.2);
+                    }
+                    counts[idx] += 1;
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * i as f64 / (num_points - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Progress", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            if method == "mean" {
+                for row in 0..rows {
+                    reporter.check_cancelled()?;
+                    for col in 0..columns {
+                        let n = counts[(row * columns + col) as usize];
+                        if n > 1 {
+                            let z = output.get_value(row, col);
+                            output.set_value(row, col, z / n as f64);
+                        }
+                    }
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Interpolation method: {}", method));
+        if method == "nearest" || method == "idw" {
+            output.add_metadata_entry(format!("Search radius: {}", radius));
+        }
+        if method == "idw" {
+            output.add_metadata_entry(format!("IDW weight: {}", weight));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the (x, y, z) values of the points in a delimited text file. Comment lines beginning
+/// with '#', and a header line, if present, are skipped, as are lines with non-numeric values.
+fn read_xyz_file(
+    file_name: &str,
+    x_field: usize,
+    y_field: usize,
+    z_field: usize,
+) -> Result<Vec<(f64, f64, f64)>, Error> {
+    let f = File::open(file_name)?;
+    let reader = BufReader::new(f);
+    let max_field = x_field.max(y_field).max(z_field);
+    let mut delimiter: Option<char> = None;
+    let mut points = vec![];
+    let mut num_skipped = 0usize;
+    let mut first_line = true;
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if delimiter.is_none() {
+            delimiter = Some(if line.contains(',') {
+                ','
+            } else if line.contains('\t') {
+                '\t'
+            } else if line.contains(';') {
+                ';'
+            } else {
+                ' '
+            });
+        }
+        let fields: Vec<&str> = if delimiter == Some(' ') {
+            line.split_whitespace().collect()
+        } else {
+            line.split(delimiter.unwrap()).map(|s| s.trim()).collect()
+        };
+        let is_first = first_line;
+        first_line = false;
+        if fields.len() <= max_field {
+            if !is_first {
+                num_skipped += 1;
+            }
+            continue;
+        }
+        match (
+            fields[x_field].parse::<f64>(),
+            fields[y_field].parse::<f64>(),
+            fields[z_field].parse::<f64>(),
+        ) {
+            (Ok(x), Ok(y), Ok(z)) => points.push((x, y, z)),
+            _ => {
+                // a non-numeric first line is a header
+                if !is_first {
+                    num_skipped += 1;
+                }
+            }
+        }
+    }
+    if num_skipped > 0 {
+        println!("Warning: {} lines could not be parsed and were skipped.", num_skipped);
+    }
+    Ok(points)
+}
@@ -2,25 +2,34 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 13/12/2017
-Last Modified: 20/01/2019
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
+use std::cmp::Ordering;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to reduce the grid resolution of a raster by a user specified amount. For example, using
 /// an aggregation factor (`--agg_factor`) of 2 would result in a raster with half the number of rows and columns.
-/// The grid cell values (`--type`) in the output image will consist of the mean, sum, maximum, minimum, or range
-/// of the overlapping grid cells in the input raster (four cells in the case of an aggregation factor of 2).
+/// The grid cell values (`--type`) in the output image will consist of the mean, sum, maximum, minimum, range,
+/// median, or mode of the overlapping grid cells in the input raster (four cells in the case of an aggregation
+/// factor of 2). The mode, i.e. the most frequently occurring value, is intended for categorical data such as
+/// land-cover classifications; ties are resolved in favour of the smallest value, and the output retains the data
+/// type and palette of the input raster. All other aggregation types produce a floating-point output.
+///
+/// Unlike `Resample`, this tool handles *NoData* explicitly. By default, an output cell is assigned *NoData* only
+/// when all of its overlapping input cells are *NoData*. The `--max_nodata` parameter sets the largest fraction of
+/// *NoData* input cells (0.0-1.0) that an output cell may contain and still receive a value; for example,
+/// `--max_nodata=0.5` assigns *NoData* to any output cell for which more than half of the contributing input cells
+/// are *NoData*. Input cells of partial blocks lying beyond the edge of the input raster are not counted.
 ///
 /// # See Also
 /// `Resample`
@@ -77,11 +86,22 @@ impl AggregateRaster {
                 "maximum".to_owned(),
                 "minimum".to_owned(),
                 "range".to_owned(),
+                "median".to_owned(),
+                "mode".to_owned(),
             ]),
             default_value: Some("mean".to_owned()),
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Maximum NoData Fraction".to_owned(),
+            flags: vec!["--max_nodata".to_owned()],
+            description: "Maximum fraction (0-1) of NoData input cells for which an output value is calculated.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let e = format!("{}", env::current_exe().unwrap().display());
         let mut parent = env::current_exe().unwrap();
@@ -96,7 +116,7 @@ impl AggregateRaster {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.tif -o=output.tif --output_text",
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.tif -o=output.tif --agg_factor=4 --type=mode --max_nodata=0.5",
             short_exe, name
         )
         .replace("*", &sep);
@@ -144,58 +164,22 @@ impl WhiteboxTool for AggregateRaster {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut agg_factor = 2isize;
-        let mut agg_type = String::from("mean");
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-agg_factor" {
-                if keyval {
-                    agg_factor = vec[1].to_string().parse::<isize>().unwrap();
-                } else {
-                    agg_factor = args[i + 1].to_string().parse::<isize>().unwrap();
-                }
-                if agg_factor < 2isize {
-                    println!(
-                        "WARNING: Aggregation factor cannot be less than 2. It has been modified."
-                    );
-                    agg_factor = 2isize;
-                }
-            } else if flag_val == "-type" {
-                if keyval {
-                    agg_type = vec[1].to_string();
-                } else {
-                    agg_type = args[i + 1].to_string();
-                }
+        let agg_type = parsed.get_string("type").unwrap_or(String::from("mean"));
+        let max_nodata = parsed.get_f64("max_nodata").unwrap_or(1f64);
+
+        if let Some(value) = parsed.get_i64("agg_factor") {
+            agg_factor = value as isize;
+            if agg_factor < 2isize {
+                println!(
+                    "WARNING: Aggregation factor cannot be less than 2. It has been modified."
+                );
+                agg_factor = 2isize;
             }
         }
 
@@ -210,16 +194,23 @@ impl WhiteboxTool for AggregateRaster {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        let stat_type = match AggregationType::parse(&agg_type) {
+            Some(t) => t,
+            None => {
+                return Err(Error::new(ErrorKind::InvalidInput, "Unrecognized aggregation type input; should be mean, sum, maximum, minimum, range, median, or mode."));
+            }
+        };
+        if max_nodata < 0f64 || max_nodata > 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --max_nodata parameter must be between 0.0 and 1.0.",
+            ));
         }
 
         if verbose {
@@ -252,273 +243,77 @@ impl WhiteboxTool for AggregateRaster {
         configs.resolution_x = input.configs.resolution_x * agg_factor as f64;
         configs.resolution_y = input.configs.resolution_y * agg_factor as f64;
         configs.nodata = nodata;
-        configs.data_type = DataType::F64;
-        configs.photometric_interp = PhotometricInterpretation::Continuous;
         configs.palette = input.configs.palette.clone();
+        if stat_type == AggregationType::Mode {
+            // the mode of a categorical raster is itself categorical
+            configs.data_type = input.configs.data_type;
+            configs.photometric_interp = input.configs.photometric_interp;
+        } else {
+            configs.data_type = DataType::F64;
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+        }
 
         let mut output = Raster::initialize_using_config(&output_file, &configs);
-        if output.configs.data_type != DataType::F32 && output.configs.data_type != DataType::F64 {
+        if stat_type != AggregationType::Mode
+            && output.configs.data_type != DataType::F32
+            && output.configs.data_type != DataType::F64
+        {
             output.configs.data_type = DataType::F32;
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
-        match agg_type.to_lowercase().trim() {
-            "mean" => {
-                for tid in 0..num_procs {
-                    let input = input.clone();
-                    let tx = tx.clone();
-                    thread::spawn(move || {
-                        let mut z: f64;
-                        let mut row_in: isize;
-                        let mut col_in: isize;
-                        let mut stat: f64;
-                        let mut count: f64;
-                        for row in (0..rows_out).filter(|r| r % num_procs == tid) {
-                            let mut data = vec![nodata; columns_out as usize];
-                            for col in 0..columns_out {
-                                row_in = row * agg_factor;
-                                col_in = col * agg_factor;
-                                stat = 0f64;
-                                count = 0f64;
-                                for r in row_in..row_in + agg_factor {
-                                    for c in col_in..col_in + agg_factor {
-                                        z = input.get_value(r, c);
-                                        if z != nodata {
-                                            stat += z;
-                                            count += 1f64;
-                                        }
-                                    }
-                                }
-                                if count > 0f64 {
-                                    stat = stat / count;
-                                    data[col as usize] = stat;
-                                }
-                            }
-                            tx.send((row, data)).unwrap();
-                        }
-                    });
-                }
-
-                for r in 0..rows_out {
-                    let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                    output.set_row_data(row, data);
-                    if verbose {
-                        progress = (100.0_f64 * r as f64 / (rows_out - 1) as f64) as usize;
-                        if progress != old_progress {
-                            println!("Progress: {}%", progress);
-                            old_progress = progress;
-                        }
-                    }
-                }
-            }
-            "sum" => {
-                for tid in 0..num_procs {
-                    let input = input.clone();
-                    let tx = tx.clone();
-                    thread::spawn(move || {
-                        let mut z: f64;
-                        let mut row_in: isize;
-                        let mut col_in: isize;
-                        let mut stat: f64;
-                        let mut count: f64;
-                        for row in (0..rows_out).filter(|r| r % num_procs == tid) {
-                            let mut data = vec![nodata; columns_out as usize];
-                            for col in 0..columns_out {
-                                row_in = row * agg_factor;
-                                col_in = col * agg_factor;
-                                stat = 0f64;
-                                count = 0f64;
-                                for r in row_in..row_in + agg_factor {
-                                    for c in col_in..col_in + agg_factor {
-                                        z = input.get_value(r, c);
-                                        if z != nodata {
-                                            stat += z;
-                                            count += 1f64;
-                                        }
-                                    }
-                                }
-                                if count > 0f64 {
-                                    data[col as usize] = stat;
-                                }
-                            }
-                            tx.send((row, data)).unwrap();
-                        }
-                    });
-                }
-
-                for r in 0..rows_out {
-                    let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                    output.set_row_data(row, data);
-                    if verbose {
-                        progress = (100.0_f64 * r as f64 / (rows_out - 1) as f64) as usize;
-                        if progress != old_progress {
-                            println!("Progress: {}%", progress);
-                            old_progress = progress;
-                        }
-                    }
-                }
-            }
-            "maximum" => {
-                for tid in 0..num_procs {
-                    let input = input.clone();
-                    let tx = tx.clone();
-                    thread::spawn(move || {
-                        let mut z: f64;
-                        let mut row_in: isize;
-                        let mut col_in: isize;
-                        let mut stat: f64;
-                        let mut count: f64;
-                        for row in (0..rows_out).filter(|r| r % num_procs == tid) {
-                            let mut data = vec![nodata; columns_out as usize];
-                            for col in 0..columns_out {
-                                row_in = row * agg_factor;
-                                col_in = col * agg_factor;
-                                stat = f64::NEG_INFINITY;
-                                count = 0f64;
-                                for r in row_in..row_in + agg_factor {
-                                    for c in col_in..col_in + agg_factor {
-                                        z = input.get_value(r, c);
-                                        if z != nodata {
-                                            if z > stat {
-                                                stat = z;
-                                            }
-                                            count += 1f64;
-                                        }
-                                    }
-                                }
-                                if count > 0f64 {
-                                    data[col as usize] = stat;
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let mut z: f64;
+                let mut row_in: isize;
+                let mut col_in: isize;
+                let mut num_cells: f64;
+                let mut values: Vec<f64> = Vec::with_capacity((agg_factor * agg_factor) as usize);
+                for row in (0..rows_out).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns_out as usize];
+                    for col in 0..columns_out {
+                        row_in = row * agg_factor;
+                        col_in = col * agg_factor;
+                        values.clear();
+                        num_cells = 0f64;
+                        for r in row_in..(row_in + agg_factor).min(rows_in) {
+                            for c in col_in..(col_in + agg_factor).min(columns_in) {
+                                num_cells += 1f64;
+                                z = input.get_value(r, c);
+                                if !is_nodata(z, nodata) {
+                                    values.push(z);
                                 }
                             }
-                            tx.send((row, data)).unwrap();
                         }
-                    });
-                }
-
-                for r in 0..rows_out {
-                    let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                    output.set_row_data(row, data);
-                    if verbose {
-                        progress = (100.0_f64 * r as f64 / (rows_out - 1) as f64) as usize;
-                        if progress != old_progress {
-                            println!("Progress: {}%", progress);
-                            old_progress = progress;
+                        if values.is_empty()
+                            || (num_cells - values.len() as f64) / num_cells > max_nodata
+                        {
+                            continue;
                         }
+                        data[col as usize] = stat_type.calculate(&mut values);
                     }
-                }
-            }
-            "minimum" => {
-                for tid in 0..num_procs {
-                    let input = input.clone();
-                    let tx = tx.clone();
-                    thread::spawn(move || {
-                        let mut z: f64;
-                        let mut row_in: isize;
-                        let mut col_in: isize;
-                        let mut stat: f64;
-                        let mut count: f64;
-                        for row in (0..rows_out).filter(|r| r % num_procs == tid) {
-                            let mut data = vec![nodata; columns_out as usize];
-                            for col in 0..columns_out {
-                                row_in = row * agg_factor;
-                                col_in = col * agg_factor;
-                                stat = f64::INFINITY;
-                                count = 0f64;
-                                for r in row_in..row_in + agg_factor {
-                                    for c in col_in..col_in + agg_factor {
-                                        z = input.get_value(r, c);
-                                        if z != nodata {
-                                            if z < stat {
-                                                stat = z;
-                                            }
-                                            count += 1f64;
-                                        }
-                                    }
-                                }
-                                if count > 0f64 {
-                                    data[col as usize] = stat;
-                                }
-                            }
-                            tx.send((row, data)).unwrap();
-                        }
-                    });
-                }
-
-                for r in 0..rows_out {
-                    let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                    output.set_row_data(row, data);
-                    if verbose {
-                        progress = (100.0_f64 * r as f64 / (rows_out - 1) as f64) as usize;
-                        if progress != old_progress {
-                            println!("Progress: {}%", progress);
-                            old_progress = progress;
-                        }
+                    if tx.send((row, data)).is_err() {
+                        return;
                     }
                 }
-            }
-            "range" => {
-                for tid in 0..num_procs {
-                    let input = input.clone();
-                    let tx = tx.clone();
-                    thread::spawn(move || {
-                        let mut z: f64;
-                        let mut row_in: isize;
-                        let mut col_in: isize;
-                        let mut max_val: f64;
-                        let mut min_val: f64;
-                        let mut count: f64;
-                        for row in (0..rows_out).filter(|r| r % num_procs == tid) {
-                            let mut data = vec![nodata; columns_out as usize];
-                            for col in 0..columns_out {
-                                row_in = row * agg_factor;
-                                col_in = col * agg_factor;
-                                max_val = f64::NEG_INFINITY;
-                                min_val = f64::INFINITY;
-                                count = 0f64;
-                                for r in row_in..row_in + agg_factor {
-                                    for c in col_in..col_in + agg_factor {
-                                        z = input.get_value(r, c);
-                                        if z != nodata {
-                                            if z > max_val {
-                                                max_val = z;
-                                            }
-                                            if z < min_val {
-                                                min_val = z;
-                                            }
-                                            count += 1f64;
-                                        }
-                                    }
-                                }
-                                if count > 0f64 {
-                                    data[col as usize] = max_val - min_val;
-                                }
-                            }
-                            tx.send((row, data)).unwrap();
-                        }
-                    });
-                }
+            });
+        }
 
-                for r in 0..rows_out {
-                    let (row, data) = rx.recv().expect("Error receiving data from thread.");
-                    output.set_row_data(row, data);
-                    if verbose {
-                        progress = (100.0_f64 * r as f64 / (rows_out - 1) as f64) as usize;
-                        if progress != old_progress {
-                            println!("Progress: {}%", progress);
-                            old_progress = progress;
-                        }
-                    }
+        for r in 0..rows_out {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows_out - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
                 }
             }
-            _ => {
-                return Err(Error::new(ErrorKind::InvalidInput, "Unrecognized aggregation type input; should be mean, sum, maximum, minimum, or range."));
-            }
         }
 
         let elapsed_time = get_formatted_elapsed_time(start);
@@ -529,6 +324,7 @@ impl WhiteboxTool for AggregateRaster {
         output.add_metadata_entry(format!("Input file: {}", input_file));
         output.add_metadata_entry(format!("Aggregation factor: {}", agg_factor));
         output.add_metadata_entry(format!("Aggregation type: {}", agg_type));
+        output.add_metadata_entry(format!("Maximum NoData fraction: {}", max_nodata));
         output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
         if verbose {
@@ -553,3 +349,74 @@ impl WhiteboxTool for AggregateRaster {
         Ok(())
     }
 }
+
+/// Block statistic, shared with the `BlockStatistics` tool.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum AggregationType {
+    Mean,
+    Sum,
+    Maximum,
+    Minimum,
+    Range,
+    Median,
+    Mode,
+}
+
+impl AggregationType {
+    pub(super) fn parse(name: &str) -> Option<AggregationType> {
+        match name.to_lowercase().trim() {
+            "mean" => Some(AggregationType::Mean),
+            "sum" => Some(AggregationType::Sum),
+            "maximum" | "max" => Some(AggregationType::Maximum),
+            "minimum" | "min" => Some(AggregationType::Minimum),
+            "range" => Some(AggregationType::Range),
+            "median" => Some(AggregationType::Median),
+            "mode" | "majority" => Some(AggregationType::Mode),
+            _ => None,
+        }
+    }
+
+    /// Calculates the statistic from the valid (non-NoData) values of a block; `values` must not
+    /// be empty and may be reordered.
+    pub(super) fn calculate(&self, values: &mut Vec<f64>) -> f64 {
+        match self {
+            AggregationType::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            AggregationType::Sum => values.iter().sum::<f64>(),
+            AggregationType::Maximum => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            AggregationType::Minimum => values.iter().cloned().fold(f64::INFINITY, f64::min),
+            AggregationType::Range => {
+                values.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+                    - values.iter().cloned().fold(f64::INFINITY, f64::min)
+            }
+            AggregationType::Median => {
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                let n = values.len();
+                if n % 2 == 1 {
+                    values[n / 2]
+                } else {
+                    (values[n / 2 - 1] + values[n / 2]) / 2f64
+                }
+            }
+            AggregationType::Mode => {
+                // after sorting, the mode is the value of the longest run; on ties, the first
+                // (i.e. smallest) value wins
+                values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                let mut mode = values[0];
+                let mut mode_count = 0usize;
+                let mut i = 0usize;
+                while i < values.len() {
+                    let mut j = i + 1;
+                    while j < values.len() && values[j] == values[i] {
+                        j += 1;
+                    }
+                    if j - i > mode_count {
+                        mode = values[i];
+                        mode_count = j - i;
+                    }
+                    i = j;
+                }
+                mode
+            }
+        }
+    }
+}
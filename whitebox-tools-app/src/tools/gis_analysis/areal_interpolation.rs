@@ -0,0 +1,771 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{point_in_poly, polygon_area};
+use whitebox_common::structures::{Array2D, Point2D};
+use whitebox_raster::*;
+use crate::tools::*;
+use whitebox_vector::*;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool re-apportions attribute totals (e.g. census population counts) from a set of source polygons
+/// (`--source`) to a set of target polygons (`--target`) with incompatible boundaries, or to a raster. This
+/// operation, known as areal interpolation, is needed whenever data that are reported for one set of
+/// zones (e.g. census tracts) must be estimated for another (e.g. watersheds or school catchments). The
+/// attributes to apportion (`--fields`; one or more numeric fields, separated by commas) must be
+/// *extensive* quantities, i.e. counts or totals rather than rates or densities.
+///
+/// The analysis is carried out on a grid. Each source total is distributed among the grid cells whose
+/// centres fall within the source polygon in proportion to the cell weights, such that the sum of the
+/// cell values within each source polygon equals the source total (the pycnophylactic, or volume-preserving,
+/// property). The cell values are then summed within each target polygon. Two weighting methods are
+/// available:
+///
+/// - **Area weighting**: If no ancillary raster is specified, all cells have equal weight and the value
+///   allocated to a target is proportional to the area of overlap with each source. The grid resolution is
+///   set using the `--cell_size` parameter.
+/// - **Dasymetric weighting**: If an ancillary raster (`--ancillary`) is specified, it defines both the grid
+///   and the cell weights. When class weights (`--class_weights`) are specified, the ancillary raster is
+///   treated as a categorical (e.g. land-cover) raster and each class is assigned a relative density,
+///   e.g. `--class_weights="1:0.0;2:1.0;3:0.25"` would prevent population from being allocated to class 1
+///   (e.g. water) and would allocate four times more population per unit area to class 2 (e.g.
+///   residential) than to class 3 (e.g. agricultural). Classes that are not listed have zero weight.
+///   Without class weights, the ancillary raster values are used directly as the weights (e.g. a
+///   building density surface); negative values are treated as zero.
+///
+/// If all of the cells within a source polygon have zero weight, the source total is distributed evenly
+/// among its cells. If a source polygon is too small to contain any cell centres, its total is allocated to
+/// the cell containing the centre of its bounding box. Likewise, the estimate for a target polygon that is
+/// too small to contain any cell centres is based on the value of the cell containing the centre of its
+/// bounding box, scaled by the ratio of the target area to the cell area.
+///
+/// When target polygons are specified, the output (`--output`) is a copy of the target polygons with the
+/// following additional fields: the estimated total of each field (named using the first six characters of
+/// the field name followed by '_EST'), the number of source polygons contributing to the target (SRC_COUNT),
+/// and the proportion of the target area covered by the source polygons (COVERAGE). Low coverage values
+/// indicate targets that extend beyond the source zones and whose estimates are likely to be underestimated.
+/// When no target polygons are specified, the output is a raster of the values allocated to each cell, and
+/// a single field may be specified. In both cases, the tool reports diagnostics describing how well the
+/// source totals were preserved, including the proportion of each source total that was allocated to the
+/// targets, the number of source polygons with zero total weight, and the number that contained no cells.
+///
+/// # See Also
+/// `Intersect`, `VectorPolygonsToRaster`, `ZonalStatistics`
+pub struct ArealInterpolation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ArealInterpolation {
+    pub fn new() -> ArealInterpolation {
+        // public constructor
+        let name = "ArealInterpolation".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description = "Re-apportions attribute totals from source polygons to target polygons or a raster using area or dasymetric weighting.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Source Polygons File".to_owned(),
+            flags: vec!["--source".to_owned()],
+            description: "Input vector polygons file containing the attribute totals.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Fields".to_owned(),
+            flags: vec!["--fields".to_owned()],
+            description: "Numeric fields to apportion, separated by commas.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Target Polygons File (optional)".to_owned(),
+            flags: vec!["--target".to_owned()],
+            description: "Input vector polygons file to which the totals are apportioned; if not specified, the output is a raster.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygons file, or raster file if no targets are specified.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::RasterAndVector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Ancillary Raster File (optional)".to_owned(),
+            flags: vec!["--ancillary".to_owned()],
+            description: "Optional ancillary raster used for dasymetric weighting.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Class Weights (optional)".to_owned(),
+            flags: vec!["--class_weights".to_owned()],
+            description: "Relative densities of the ancillary raster classes, e.g. '1:0.0;2:1.0;3:0.25'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Grid resolution used for area weighting when no ancillary raster is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --source=tracts.shp --fields=\"POP,HOUSEHOLDS\" --target=watersheds.shp -o=output.shp --ancillary=landcover.tif --class_weights=\"1:0.0;2:1.0;3:0.25\"",
+            short_exe, name
+        ).replace("*", &sep);
+
+        ArealInterpolation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ArealInterpolation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut source_file = parsed.get_string("source").unwrap_or_default();
+        let fields_str = parsed.get_string("fields").unwrap_or_default();
+        let mut target_file = parsed.get_string("target").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut ancillary_file = parsed.get_string("ancillary").unwrap_or_default();
+        let class_weights_str = parsed.get_string("class_weights").unwrap_or_default();
+        let cell_size = parsed.get_f64("cell_size").unwrap_or(0f64);
+
+        // read the arguments
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        source_file = resolve_input_path(&source_file, working_directory)?;
+        target_file = resolve_input_path(&target_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        ancillary_file = resolve_input_path(&ancillary_file, working_directory)?;
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let source = Shapefile::read(&source_file)?;
+        if source.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The source vector data must be of polygon base shape type.",
+            ));
+        }
+
+        let fields: Vec<String> = fields_str
+            .split(",")
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty())
+            .collect();
+        if fields.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one field (--fields) must be specified.",
+            ));
+        }
+        for field in &fields {
+            match source.attributes.get_field_num(field) {
+                Some(i) if source.attributes.is_field_numeric(i) => {}
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "The field {} is not a numeric field of the source attribute table.",
+                            field
+                        ),
+                    ))
+                }
+            }
+        }
+        if target_file.is_empty() && fields.len() > 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Only a single field may be interpolated to a raster; specify target polygons to apportion multiple fields.",
+            ));
+        }
+
+        let class_weights = parse_class_weights(&class_weights_str)?;
+        if !class_weights.is_empty() && ancillary_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Class weights (--class_weights) can only be used with an ancillary raster (--ancillary).",
+            ));
+        }
+
+        // create the analysis grid and the cell weights
+        let mut grid = if !ancillary_file.is_empty() {
+            let ancillary = Raster::new(&ancillary_file, "r")?;
+            let mut grid = Raster::initialize_using_file(&output_file, &ancillary);
+            let nodata = ancillary.configs.nodata;
+            for row in 0..ancillary.configs.rows as isize {
+                reporter.check_cancelled()?;
+                for col in 0..ancillary.configs.columns as isize {
+                    let z = ancillary.get_value(row, col);
+                    let w = if is_nodata(z, nodata) {
+                        0f64
+                    } else if !class_weights.is_empty() {
+                        match class_weights.get(&(z.round() as i64)) {
+                            Some(w) => *w,
+                            None => 0f64,
+                        }
+                    } else {
+                        z.max(0f64)
+                    };
+                    grid.set_value(row, col, w);
+                }
+            }
+            grid
+        } else {
+            if cell_size <= 0f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Either an ancillary raster (--ancillary) or a positive cell size (--cell_size) must be specified.",
+                ));
+            }
+            let west: f64 = source.header.x_min;
+            let north: f64 = source.header.y_max;
+            let rows: isize = (((north - source.header.y_min) / cell_size).ceil()) as isize;
+            let columns: isize = (((source.header.x_max - west) / cell_size).ceil()) as isize;
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = rows as usize;
+            configs.columns = columns as usize;
+            configs.north = north;
+            configs.south = north - rows as f64 * cell_size;
+            configs.east = west + columns as f64 * cell_size;
+            configs.west = west;
+            configs.resolution_x = cell_size;
+            configs.resolution_y = cell_size;
+            configs.nodata = -32768f64;
+            configs.data_type = DataType::F32;
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            configs.set_crs(&whitebox_common::spatial_ref_system::CoordinateReferenceSystem::from_wkt(&source.projection));
+            let mut grid = Raster::initialize_using_config(&output_file, &configs);
+            grid.reinitialize_values(1f64);
+            grid
+        };
+        grid.configs.nodata = -32768f64;
+        grid.configs.data_type = DataType::F32;
+        grid.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        let rows = grid.configs.rows as isize;
+        let columns = grid.configs.columns as isize;
+        let cell_area = grid.configs.resolution_x * grid.configs.resolution_y;
+
+        if verbose {
+            println!("Rasterizing source polygons...")
+        };
+        let source_ids = rasterize_polygons(&source, &grid)?;
+
+        // source totals and weights
+        let num_fields = fields.len();
+        let mut totals = vec![vec![0f64; num_fields]; source.num_records];
+        for record_num in 0..source.num_records {
+            reporter.check_cancelled()?;
+            for f in 0..num_fields {
+                totals[record_num][f] = match source.attributes.get_value(record_num, &fields[f]) {
+                    FieldData::Int(v) => v as f64,
+                    FieldData::Real(v) => v,
+                    _ => 0f64,
+                };
+            }
+        }
+        let mut sum_weights = vec![0f64; source.num_records];
+        let mut num_cells = vec![0usize; source.num_records];
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let id = source_ids.get_value(row, col);
+                if id >= 0 {
+                    sum_weights[id as usize] += grid.get_value(row, col);
+                    num_cells[id as usize] += 1;
+                }
+            }
+        }
+
+        // sources too small to contain any cell centres are assigned to the cell containing
+        // the centre of their bounding box
+        let mut small_sources: HashMap<(isize, isize), Vec<usize>> = HashMap::new();
+        let mut num_zero_weight = 0;
+        let mut num_no_cells = 0;
+        let mut num_outside = 0;
+        for record_num in 0..source.num_records {
+            reporter.check_cancelled()?;
+            if num_cells[record_num] == 0 {
+                let record = source.get_record(record_num);
+                if record.shape_type == ShapeType::Null {
+                    continue;
+                }
+                num_no_cells += 1;
+                let row = grid.get_row_from_y((record.y_min + record.y_max) / 2f64);
+                let col = grid.get_column_from_x((record.x_min + record.x_max) / 2f64);
+                if row >= 0 && row < rows && col >= 0 && col < columns {
+                    small_sources.entry((row, col)).or_insert(vec![]).push(record_num);
+                } else {
+                    num_outside += 1;
+                }
+            } else if sum_weights[record_num] <= 0f64 {
+                num_zero_weight += 1;
+            }
+        }
+
+        // allocate the source totals to the cells
+        let mut allocated: Vec<Array2D<f64>> = Vec::with_capacity(num_fields);
+        for _ in 0..num_fields {
+            allocated.push(Array2D::new(rows, columns, 0f64, 0f64)?);
+        }
+        for row in 0..rows {
+            for col in 0..columns {
+                let id = source_ids.get_value(row, col);
+                if id >= 0 {
+                    let id = id as usize;
+                    let proportion = if sum_weights[id] > 0f64 {
+                        grid.get_value(row, col) / sum_weights[id]
+                    } else {
+                        1f64 / num_cells[id] as f64
+                    };
+                    for f in 0..num_fields {
+                        allocated[f].set_value(row, col, totals[id][f] * proportion);
+                    }
+                }
+                if let Some(ids) = small_sources.get(&(row, col)) {
+                    for &id in ids {
+                        for f in 0..num_fields {
+                            allocated[f].increment(row, col, totals[id][f]);
+                        }
+                    }
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Allocating totals", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let source_sum: Vec<f64> = (0..num_fields)
+            .map(|f| totals.iter().map(|t| t[f]).sum())
+            .collect();
+
+        if target_file.is_empty() {
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    let id = source_ids.get_value(row, col);
+                    if id >= 0 || small_sources.contains_key(&(row, col)) {
+                        grid.set_value(row, col, allocated[0].get_value(row, col));
+                    } else {
+                        grid.set_value(row, col, -32768f64);
+                    }
+                }
+            }
+            let mut output_sum = 0f64;
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    output_sum += allocated[0].get_value(row, col);
+                }
+            }
+            if verbose {
+                print_diagnostics(
+                    &fields,
+                    &source_sum,
+                    &[output_sum],
+                    num_zero_weight,
+                    num_no_cells,
+                    num_outside,
+                );
+            }
+
+            let elapsed_time = get_formatted_elapsed_time(start);
+            grid.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            grid.add_metadata_entry(format!("Source file: {}", source_file));
+            grid.add_metadata_entry(format!("Field: {}", fields[0]));
+            if !ancillary_file.is_empty() {
+                grid.add_metadata_entry(format!("Ancillary file: {}", ancillary_file));
+            }
+            grid.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match grid.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+            if verbose {
+                println!(
+                    "{}",
+                    &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+                );
+            }
+            return Ok(());
+        }
+
+        // aggregate the cell values within the target polygons
+        let target = Shapefile::read(&target_file)?;
+        if target.header.shape_type.base_shape_type() != ShapeType::Polygon {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The target vector data must be of polygon base shape type.",
+            ));
+        }
+        if verbose {
+            println!("Rasterizing target polygons...")
+        };
+        let target_ids = rasterize_polygons(&target, &grid)?;
+        let mut estimates = vec![vec![0f64; num_fields]; target.num_records];
+        let mut target_cells = vec![0usize; target.num_records];
+        let mut covered_cells = vec![0usize; target.num_records];
+        let mut contributing: Vec<HashSet<i32>> = vec![HashSet::new(); target.num_records];
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let id = target_ids.get_value(row, col);
+                if id >= 0 {
+                    let id = id as usize;
+                    target_cells[id] += 1;
+                    let src = source_ids.get_value(row, col);
+                    if src >= 0 {
+                        covered_cells[id] += 1;
+                        contributing[id].insert(src);
+                    }
+                    if let Some(ids) = small_sources.get(&(row, col)) {
+                        for &s in ids {
+                            contributing[id].insert(s as i32);
+                        }
+                    }
+                    for f in 0..num_fields {
+                        estimates[id][f] += allocated[f].get_value(row, col);
+                    }
+                }
+            }
+        }
+
+        let mut output = Shapefile::initialize_using_file(
+            &output_file,
+            &target,
+            target.header.shape_type,
+            true,
+        )?;
+        for field in &fields {
+            let name = format!("{}_EST", field.chars().take(6).collect::<String>());
+            output
+                .attributes
+                .add_field(&AttributeField::new(&name, FieldDataType::Real, 18u8, 4u8));
+        }
+        output
+            .attributes
+            .add_field(&AttributeField::new("SRC_COUNT", FieldDataType::Int, 7u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("COVERAGE", FieldDataType::Real, 8u8, 4u8));
+
+        let mut target_sum = vec![0f64; num_fields];
+        for record_num in 0..target.num_records {
+            let record = target.get_record(record_num);
+            let mut atts = target.attributes.get_record(record_num);
+            let mut coverage = if target_cells[record_num] > 0 {
+                covered_cells[record_num] as f64 / target_cells[record_num] as f64
+            } else {
+                0f64
+            };
+            if target_cells[record_num] == 0 && record.shape_type != ShapeType::Null {
+                // the target is smaller than a grid cell
+                let row = grid.get_row_from_y((record.y_min + record.y_max) / 2f64);
+                let col = grid.get_column_from_x((record.x_min + record.x_max) / 2f64);
+                if row >= 0 && row < rows && col >= 0 && col < columns {
+                    let scale = (record_area(record) / cell_area).min(1f64);
+                    for f in 0..num_fields {
+                        estimates[record_num][f] = allocated[f].get_value(row, col) * scale;
+                    }
+                    let src = source_ids.get_value(row, col);
+                    if src >= 0 {
+                        contributing[record_num].insert(src);
+                        coverage = 1f64;
+                    }
+                }
+            }
+            for f in 0..num_fields {
+                atts.push(FieldData::Real(estimates[record_num][f]));
+                target_sum[f] += estimates[record_num][f];
+            }
+            atts.push(FieldData::Int(contributing[record_num].len() as i32));
+            atts.push(FieldData::Real(coverage));
+            output.add_record(record.clone());
+            output.attributes.add_record(atts, false);
+
+            reporter.check_cancelled()?;
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / target.num_records as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Summarizing targets", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            print_diagnostics(
+                &fields,
+                &source_sum,
+                &target_sum,
+                num_zero_weight,
+                num_no_cells,
+                num_outside,
+            );
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a list of class weights of the form 'class:weight;class:weight'.
+fn parse_class_weights(s: &str) -> Result<HashMap<i64, f64>, Error> {
+    let mut class_weights = HashMap::new();
+    for entry in s.split(";").map(|e| e.trim()).filter(|e| !e.is_empty()) {
+        let vals: Vec<&str> = entry.split(":").collect();
+        if vals.len() != 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid class weight '{}'; expected 'class:weight'.", entry),
+            ));
+        }
+        let class = vals[0].trim().parse::<f64>();
+        let weight = vals[1].trim().parse::<f64>();
+        match (class, weight) {
+            (Ok(c), Ok(w)) if w >= 0f64 => {
+                class_weights.insert(c.round() as i64, w);
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Invalid class weight '{}'; weights must be non-negative numbers.", entry),
+                ))
+            }
+        }
+    }
+    Ok(class_weights)
+}
+
+/// Assigns each grid cell the index of the polygon containing its centre, or -1 if it is not
+/// contained by any polygon. Holes are handled using the even-odd rule.
+fn rasterize_polygons(polygons: &Shapefile, grid: &Raster) -> Result<Array2D<i32>, Error> {
+    let rows = grid.configs.rows as isize;
+    let columns = grid.configs.columns as isize;
+    let mut ids: Array2D<i32> = Array2D::new(rows, columns, -1i32, -1i32)?;
+    for record_num in 0..polygons.num_records {
+        let record = polygons.get_record(record_num);
+        if record.shape_type == ShapeType::Null {
+            continue;
+        }
+        let starting_row = grid.get_row_from_y(record.y_max).max(0);
+        let ending_row = grid.get_row_from_y(record.y_min).min(rows - 1);
+        let starting_col = grid.get_column_from_x(record.x_min).max(0);
+        let ending_col = grid.get_column_from_x(record.x_max).min(columns - 1);
+        for row in starting_row..=ending_row {
+            let y = grid.get_y_from_row(row);
+            for col in starting_col..=ending_col {
+                let x = grid.get_x_from_column(col);
+                let p = Point2D::new(x, y);
+                let mut inside = false;
+                for part in 0..record.num_parts as usize {
+                    let start = record.parts[part] as usize;
+                    let end = if part < record.num_parts as usize - 1 {
+                        record.parts[part + 1] as usize
+                    } else {
+                        record.num_points as usize
+                    };
+                    if point_in_poly(&p, &record.points[start..end]) {
+                        inside = !inside;
+                    }
+                }
+                if inside {
+                    ids.set_value(row, col, record_num as i32);
+                }
+            }
+        }
+    }
+    Ok(ids)
+}
+
+/// Returns the area of a polygon record, i.e. the area of its outer rings less that of its holes.
+fn record_area(record: &ShapefileGeometry) -> f64 {
+    let mut area = 0f64;
+    for part in 0..record.num_parts as usize {
+        let start = record.parts[part] as usize;
+        let end = if part < record.num_parts as usize - 1 {
+            record.parts[part + 1] as usize
+        } else {
+            record.num_points as usize
+        };
+        if record.is_hole(part as i32) {
+            area -= polygon_area(&record.points[start..end]);
+        } else {
+            area += polygon_area(&record.points[start..end]);
+        }
+    }
+    area
+}
+
+fn print_diagnostics(
+    fields: &[String],
+    source_sum: &[f64],
+    output_sum: &[f64],
+    num_zero_weight: usize,
+    num_no_cells: usize,
+    num_outside: usize,
+) {
+    println!("Volume preservation:");
+    for f in 0..fields.len() {
+        let percent = if source_sum[f] != 0f64 {
+            100f64 * output_sum[f] / source_sum[f]
+        } else {
+            100f64
+        };
+        println!(
+            "  {}: {:.4} of {:.4} allocated ({:.3}%)",
+            fields[f], output_sum[f], source_sum[f], percent
+        );
+    }
+    println!(
+        "Source polygons with zero total weight (evenly distributed): {}",
+        num_zero_weight
+    );
+    println!(
+        "Source polygons containing no cell centres: {} ({} outside of the grid and not allocated)",
+        num_no_cells, num_outside
+    );
+}
@@ -46,7 +46,7 @@ impl AverageOverlay {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input Files".to_owned(),
-            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--inputs".to_owned()],
             description: "Input raster files.".to_owned(),
             parameter_type: ParameterType::FileList(ParameterFileType::Raster),
             default_value: None,
@@ -120,40 +120,11 @@ impl WhiteboxTool for AverageOverlay {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" || flag_val == "-input" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -166,14 +137,10 @@ impl WhiteboxTool for AverageOverlay {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut cmd = input_files.split(";");
         let mut vec = cmd.collect::<Vec<&str>>();
@@ -208,9 +175,7 @@ impl WhiteboxTool for AverageOverlay {
                 };
 
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 let input = Raster::new(&input_file, "r")?;
                 in_nodata = input.configs.nodata;
                 if !read_first_file {
@@ -226,15 +191,14 @@ impl WhiteboxTool for AverageOverlay {
                 // check to ensure that all inputs have the same rows and columns
                 if input.configs.rows as isize != rows || input.configs.columns as isize != columns
                 {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                "The input files must have the same number of rows and columns and spatial extent."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
                 }
 
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != in_nodata {
-                            if output[(row, col)] != out_nodata {
+                        if !is_nodata(z, in_nodata) {
+                            if !is_nodata(output[(row, col)], out_nodata) {
                                 output.increment(row, col, z);
                                 n.increment(row, col, 1i16);
                             } else {
@@ -243,10 +207,11 @@ impl WhiteboxTool for AverageOverlay {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress (loop {} of {}): {}%", i, num_files + 1, progress);
+                            reporter.progress(&format!("Progress (loop {} of {})", i, num_files + 1), progress);
                             old_progress = progress;
                         }
                     }
@@ -258,7 +223,7 @@ impl WhiteboxTool for AverageOverlay {
         for row in 0..rows {
             for col in 0..columns {
                 z = output[(row, col)];
-                if z != out_nodata {
+                if !is_nodata(z, out_nodata) {
                     if n[(row, col)] > 0i16 {
                         output[(row, col)] = z / n[(row, col)] as f64;
                     } else {
@@ -266,15 +231,11 @@ impl WhiteboxTool for AverageOverlay {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!(
-                        "Progress (loop {} of {}): {}%",
-                        num_files + 1,
-                        num_files + 1,
-                        progress
-                    );
+                    reporter.progress(&format!("Progress (loop {} of {})", num_files + 1, num_files + 1), progress);
                     old_progress = progress;
                 }
             }
@@ -51,7 +51,7 @@ impl BlockMaximumGridding {
                 "--input".to_string(),
             ),
             default_value: None,
-            optional: false,
+            optional: true,
         });
 
         parameters.push(ToolParameter {
@@ -149,72 +149,15 @@ impl WhiteboxTool for BlockMaximumGridding {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut field_name = String::new();
-        let mut use_z = false;
-        let mut output_file = String::new();
-        let mut grid_res = 0f64;
-        let mut base_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                field_name = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-use_z" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    use_z = true;
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-cell_size" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-base" {
-                base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let field_name = parsed.get_string("field").unwrap_or_default();
+        let use_z = parsed.get_bool("use_z");
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let grid_res = parsed.get_f64("cell_size").unwrap_or(0f64);
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -227,17 +170,11 @@ impl WhiteboxTool for BlockMaximumGridding {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -261,9 +198,7 @@ impl WhiteboxTool for BlockMaximumGridding {
         let nodata = -32768.0f64;
 
         let mut output = if !base_file.trim().is_empty() || grid_res == 0f64 {
-            if !base_file.contains(&sep) && !base_file.contains("/") {
-                base_file = format!("{}{}", working_directory, base_file);
-            }
+            base_file = resolve_input_path(&base_file, working_directory)?;
             let mut base = Raster::new(&base_file, "r")?;
             base.configs.nodata = nodata;
             Raster::initialize_using_file(&output_file, &base)
@@ -348,16 +283,17 @@ impl WhiteboxTool for BlockMaximumGridding {
                 col = (((columns - 1) as f64 * (x - west) / ew_range).floor()) as isize;
                 row = (((rows - 1) as f64 * (north - y) / ns_range).floor()) as isize;
                 z_current = output.get_value(row, col);
-                if z_current == nodata || z > z_current {
+                if is_nodata(z_current, nodata) || z > z_current {
                     output.set_value(row, col, z);
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * record_num as f64
                         / (vector_data.num_records - 1) as f64)
                         as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -384,17 +320,18 @@ impl WhiteboxTool for BlockMaximumGridding {
                     row = (((rows - 1) as f64 * (north - y) / ns_range).floor()) as isize;
 
                     z_current = output.get_value(row, col);
-                    if z_current == nodata || z > z_current {
+                    if is_nodata(z_current, nodata) || z > z_current {
                         output.set_value(row, col, z);
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * record_num as f64
                         / (vector_data.num_records - 1) as f64)
                         as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
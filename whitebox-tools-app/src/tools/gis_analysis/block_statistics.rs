@@ -0,0 +1,366 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::aggregate_raster::AggregationType;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool calculates a statistic (`--type`) over non-overlapping rectangular blocks of grid cells in an
+/// input raster (`--input`) and assigns the result to every cell of the block, such that the output raster
+/// has the same extent and resolution as the input. It is therefore equivalent to running `AggregateRaster`
+/// and resampling the result back onto the original grid, and is useful for comparing a fine-resolution
+/// variable with its coarse-scale context or for creating zones for scale-transfer analyses. Blocks are
+/// `--block_size` grid cells on each side, or `--block_x` columns by `--block_y` rows if either is specified,
+/// and are anchored at the upper-left corner of the raster; blocks along the right and bottom edges may
+/// therefore be truncated.
+///
+/// The supported statistics are the mean, sum, maximum, minimum, range, median, and mode (the most frequently
+/// occurring value, intended for categorical data). *NoData* cells are excluded from the calculation. A block
+/// for which the fraction of *NoData* cells exceeds `--max_nodata` (0.0-1.0; by default 1.0, i.e. only blocks
+/// that are entirely *NoData*) is assigned *NoData*. Cells that are *NoData* in the input raster receive the
+/// block value unless the `--preserve_nodata` flag is specified.
+///
+/// # See Also
+/// `AggregateRaster`, `DisaggregateRaster`, `BlockMinimumGridding`, `BlockMaximumGridding`
+pub struct BlockStatistics {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl BlockStatistics {
+    pub fn new() -> BlockStatistics {
+        // public constructor
+        let name = "BlockStatistics".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Calculates statistics over non-overlapping blocks of cells, output at the original resolution.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Block Size (cells)".to_owned(),
+            flags: vec!["--block_size".to_owned()],
+            description: "Block size, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Block X Dimension (cells)".to_owned(),
+            flags: vec!["--block_x".to_owned()],
+            description: "Optional block width, in columns; overrides the block size.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Block Y Dimension (cells)".to_owned(),
+            flags: vec!["--block_y".to_owned()],
+            description: "Optional block height, in rows; overrides the block size.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Statistic Type".to_owned(),
+            flags: vec!["--type".to_owned()],
+            description: "Statistic calculated for each block.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "mean".to_owned(),
+                "sum".to_owned(),
+                "maximum".to_owned(),
+                "minimum".to_owned(),
+                "range".to_owned(),
+                "median".to_owned(),
+                "mode".to_owned(),
+            ]),
+            default_value: Some("mean".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum NoData Fraction".to_owned(),
+            flags: vec!["--max_nodata".to_owned()],
+            description: "Maximum fraction (0-1) of NoData cells in a block for which a statistic is calculated.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Preserve NoData cells?".to_owned(),
+            flags: vec!["--preserve_nodata".to_owned()],
+            description: "Keep input NoData cells as NoData in the output?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.tif -o=output.tif --block_size=25 --type=median --max_nodata=0.5",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        BlockStatistics {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for BlockStatistics {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let block_size = parsed.get_i64("block_size").map(|v| v as isize).unwrap_or(10isize);
+        let mut block_x = parsed.get_i64("block_x").map(|v| v as isize).unwrap_or(0isize);
+        let mut block_y = parsed.get_i64("block_y").map(|v| v as isize).unwrap_or(0isize);
+        let stat_name = parsed.get_string("type").unwrap_or(String::from("mean"));
+        let max_nodata = parsed.get_f64("max_nodata").unwrap_or(1f64);
+        let preserve_nodata = parsed.get_bool("preserve_nodata");
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let stat_type = match AggregationType::parse(&stat_name) {
+            Some(t) => t,
+            None => {
+                return Err(Error::new(ErrorKind::InvalidInput, "Unrecognized statistic type input; should be mean, sum, maximum, minimum, range, median, or mode."));
+            }
+        };
+        if max_nodata < 0f64 || max_nodata > 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --max_nodata parameter must be between 0.0 and 1.0.",
+            ));
+        }
+        if block_x < 1 {
+            block_x = block_size;
+        }
+        if block_y < 1 {
+            block_y = block_size;
+        }
+        if block_x < 1 || block_y < 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The block dimensions must be at least one grid cell.",
+            ));
+        }
+
+        for file in [&mut input_file, &mut output_file] {
+            *file = resolve_path(file, working_directory);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let nodata = input.configs.nodata;
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let block_rows = (rows as f64 / block_y as f64).ceil() as isize;
+        let block_columns = (columns as f64 / block_x as f64).ceil() as isize;
+
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let mut z: f64;
+                let mut num_cells: f64;
+                let mut values: Vec<f64> = Vec::with_capacity((block_x * block_y) as usize);
+                for block_row in (0..block_rows).filter(|r| r % num_procs == tid) {
+                    let row_start = block_row * block_y;
+                    let row_end = (row_start + block_y).min(rows);
+                    let mut data = vec![nodata; block_columns as usize];
+                    for block_col in 0..block_columns {
+                        let col_start = block_col * block_x;
+                        let col_end = (col_start + block_x).min(columns);
+                        values.clear();
+                        num_cells = 0f64;
+                        for r in row_start..row_end {
+                            for c in col_start..col_end {
+                                num_cells += 1f64;
+                                z = input.get_value(r, c);
+                                if !is_nodata(z, nodata) {
+                                    values.push(z);
+                                }
+                            }
+                        }
+                        if values.is_empty()
+                            || (num_cells - values.len() as f64) / num_cells > max_nodata
+                        {
+                            continue;
+                        }
+                        data[block_col as usize] = stat_type.calculate(&mut values);
+                    }
+                    if tx.send((block_row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        if stat_type != AggregationType::Mode {
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            if output.configs.data_type != DataType::F32 && output.configs.data_type != DataType::F64 {
+                output.configs.data_type = DataType::F32;
+            }
+        }
+        for b in 0..block_rows {
+            let (block_row, data) = rx.recv().expect("Error receiving data from thread.");
+            let row_start = block_row * block_y;
+            let row_end = (row_start + block_y).min(rows);
+            for row in row_start..row_end {
+                for col in 0..columns {
+                    if preserve_nodata && is_nodata(input.get_value(row, col), nodata) {
+                        continue;
+                    }
+                    output.set_value(row, col, data[(col / block_x) as usize]);
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * b as f64 / (block_rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Block size: {} x {}", block_x, block_y));
+        output.add_metadata_entry(format!("Statistic: {}", stat_name));
+        output.add_metadata_entry(format!("Maximum NoData fraction: {}", max_nodata));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tools calculates a type of shape complexity index for raster objects, focused on the complexity of the
 /// boundary of polygons. The index uses the `LineThinning` tool to estimate a skeletonized network for each
@@ -129,40 +128,11 @@ impl WhiteboxTool for BoundaryShapeComplexity {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -175,18 +145,12 @@ impl WhiteboxTool for BoundaryShapeComplexity {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...");
@@ -204,27 +168,25 @@ impl WhiteboxTool for BoundaryShapeComplexity {
 
         let start = Instant::now();
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        if input[(row, col)] > 0.0 && input[(row, col)] != nodata {
+                        if input[(row, col)] > 0.0 && !is_nodata(input[(row, col)], nodata) {
                             data[col as usize] = 1.0;
                         } else if input[(row, col)] == 0.0 {
                             data[col as usize] = 0.0;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -244,10 +206,11 @@ impl WhiteboxTool for BoundaryShapeComplexity {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Initializing output: {}%", progress);
+                    reporter.progress("Initializing output", progress);
                     old_progress = progress;
                 }
             }
@@ -285,7 +248,7 @@ impl WhiteboxTool for BoundaryShapeComplexity {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = output[(row, col)];
-                        if z > 0.0 && z != nodata {
+                        if z > 0.0 && !is_nodata(z, nodata) {
                             // fill the neighbours array
                             for i in 0..8 {
                                 neighbours[i] = output[(row + dy[i], col + dx[i])];
@@ -318,10 +281,11 @@ impl WhiteboxTool for BoundaryShapeComplexity {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (a + 1) as f64 / 4.0) as usize;
                     if progress != old_progress {
-                        println!("Loop Number {}: {}%", loop_num, progress);
+                        reporter.progress(&format!("Loop Number {}", loop_num), progress);
                         old_progress = progress;
                     }
                 }
@@ -487,10 +451,11 @@ impl WhiteboxTool for BoundaryShapeComplexity {
                 //     }
                 // }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating Index: {}%", progress);
+                    reporter.progress("Calculating Index", progress);
                     old_progress = progress;
                 }
             }
@@ -520,17 +485,18 @@ impl WhiteboxTool for BoundaryShapeComplexity {
         for row in 0..rows {
             for col in 0..columns {
                 z = input[(row, col)];
-                if z != nodata && z != 0f64 {
+                if !is_nodata(z, nodata) && z != 0f64 {
                     bin = (z - min_val).floor() as usize;
                     output[(row, col)] = num_end_nodes[bin];
                 } else if z == 0f64 {
                     output[(row, col)] = 0f64;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating Index: {}%", progress);
+                    reporter.progress("Calculating Index", progress);
                     old_progress = progress;
                 }
             }
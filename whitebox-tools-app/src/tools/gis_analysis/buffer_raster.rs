@@ -11,7 +11,7 @@ use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to identify an area of interest within a specified distance of
@@ -78,7 +78,7 @@ impl BufferRaster {
             flags: vec!["--size".to_owned()],
             description: "Buffer size.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: None,
+            default_value: Some("10.0".to_owned()),
             optional: false,
         });
 
@@ -153,58 +153,13 @@ impl WhiteboxTool for BufferRaster {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut buffer_size: f64 = 10.0;
-        let mut grid_cell_units = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-size" {
-                if keyval {
-                    buffer_size = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    buffer_size = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-gridcells" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    grid_cell_units = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let buffer_size: f64 = parsed.get_f64("size").unwrap_or(10.0);
+        let grid_cell_units = parsed.get_bool("gridcells");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -217,17 +172,11 @@ impl WhiteboxTool for BufferRaster {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -258,16 +207,17 @@ impl WhiteboxTool for BufferRaster {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata(z, nodata) {
                     output[(row, col)] = 0.0;
                 } else {
                     output[(row, col)] = inf_val;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Initializing Rasters: {}%", progress);
+                    reporter.progress("Initializing Rasters", progress);
                     old_progress = progress;
                 }
             }
@@ -283,7 +233,7 @@ impl WhiteboxTool for BufferRaster {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = output[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata(z2, nodata) {
                             h = match i {
                                 0 => 2.0 * r_x[(y, x)] + 1.0,
                                 1 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
@@ -306,10 +256,11 @@ impl WhiteboxTool for BufferRaster {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (1 of 3): {}%", progress);
+                    reporter.progress("Progress (1 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -325,7 +276,7 @@ impl WhiteboxTool for BufferRaster {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = output[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata(z2, nodata) {
                             h = match i {
                                 5 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
                                 4 => 2.0 * r_x[(y, x)] + 1.0,
@@ -348,10 +299,11 @@ impl WhiteboxTool for BufferRaster {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (2 of 3): {}%", progress);
+                    reporter.progress("Progress (2 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -366,7 +318,7 @@ impl WhiteboxTool for BufferRaster {
         for row in 0..rows {
             for col in 0..columns {
                 z = input[(row, col)];
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     dist = output[(row, col)].sqrt() * cell_size;
                     if dist <= buffer_size {
                         output[(row, col)] = 1.0;
@@ -377,10 +329,11 @@ impl WhiteboxTool for BufferRaster {
                     output[(row, col)] = nodata;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (3 of 3): {}%", progress);
+                    reporter.progress("Progress (3 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,358 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{calibrate_measures, cumulative_distances};
+use crate::tools::*;
+use whitebox_vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool assigns measures (M-values) to the vertices of vector polyline routes, e.g. roads or
+/// streams, for use in linear referencing workflows such as `LocateRouteEvents`. By default, the
+/// measure of each vertex is its distance along the route, starting at zero, multiplied by the
+/// optional `--factor` (e.g. 0.001 to convert metres to kilometres). Alternatively, the start and
+/// end measures of each route may be read from two numeric fields of the input attribute table
+/// (`--from_field` and `--to_field`), in which case intermediate vertices are assigned measures
+/// proportional to their distance along the route. Measures are continuous across the parts of
+/// multi-part routes, ignoring the gaps between parts.
+///
+/// The output is a PolyLineM file (or PolyLineZ, if the input contains z-values) with the same
+/// attribute table as the input.
+///
+/// # See Also
+/// `LocateRouteEvents`, `LinesToPoints`
+pub struct CalibrateRoutes {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl CalibrateRoutes {
+    pub fn new() -> CalibrateRoutes {
+        // public constructor
+        let name = "CalibrateRoutes".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description = "Assigns linear referencing measures to the vertices of vector polyline routes.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Routes File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector polyline routes file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output measured vector polyline file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "From-Measure Field".to_owned(),
+            flags: vec!["--from_field".to_owned()],
+            description: "Numeric field containing the measure of the start of each route (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "To-Measure Field".to_owned(),
+            flags: vec!["--to_field".to_owned()],
+            description: "Numeric field containing the measure of the end of each route (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Distance Units Factor".to_owned(),
+            flags: vec!["--factor".to_owned()],
+            description: "Multiplier applied to distances when measures are derived from route length.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=roads.shp -o=routes.shp --factor=0.001
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=roads.shp -o=routes.shp --from_field=BEGIN_KM --to_field=END_KM",
+            short_exe, name
+        ).replace("*", &sep);
+
+        CalibrateRoutes {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for CalibrateRoutes {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let from_field = parsed.get_string("from_field").unwrap_or_default();
+        let to_field = parsed.get_string("to_field").unwrap_or_default();
+        let factor = parsed.get_f64("factor").unwrap_or(1f64);
+
+        // read the arguments
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+
+        // make sure the input vector file is of polyline type
+        if input.header.shape_type.base_shape_type() != ShapeType::PolyLine {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of a POLYLINE base shape type.",
+            ));
+        }
+
+        let use_fields = !from_field.is_empty() || !to_field.is_empty();
+        if use_fields {
+            for field in [&from_field, &to_field] {
+                match input.attributes.get_field_num(field) {
+                    Some(i) if input.attributes.is_field_numeric(i) => {}
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "Both the from- and to-measure fields must be numeric fields of the input attribute table.",
+                        ))
+                    }
+                }
+            }
+        }
+
+        let output_type = if input.header.shape_type.dimension() == ShapeTypeDimension::Z {
+            ShapeType::PolyLineZ
+        } else {
+            ShapeType::PolyLineM
+        };
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, output_type, true)?;
+
+        let mut num_skipped = 0;
+        for record_num in 0..input.num_records {
+            let record = input.get_record(record_num);
+            if record.shape_type == ShapeType::Null || record.num_points < 2 {
+                num_skipped += 1;
+                continue;
+            }
+
+            // measures are first calculated as continuous distances along the route
+            let mut measures: Vec<f64> = Vec::with_capacity(record.num_points as usize);
+            let mut length = 0f64;
+            for part in 0..record.num_parts as usize {
+                let part_start = record.parts[part] as usize;
+                let part_end = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize
+                } else {
+                    record.num_points as usize
+                };
+                let distances = cumulative_distances(&record.points[part_start..part_end], length);
+                length = *distances.last().unwrap_or(&length);
+                measures.extend(distances);
+            }
+
+            if use_fields {
+                let from_m = field_to_f64(&input.attributes.get_value(record_num, &from_field));
+                let to_m = field_to_f64(&input.attributes.get_value(record_num, &to_field));
+                if from_m.is_none() || to_m.is_none() {
+                    num_skipped += 1;
+                    continue;
+                }
+                let (from_m, to_m) = (from_m.unwrap(), to_m.unwrap());
+                if record.num_parts == 1 {
+                    measures = calibrate_measures(&record.points, from_m, to_m);
+                } else {
+                    for m in measures.iter_mut() {
+                        *m = if length > 0f64 {
+                            from_m + (to_m - from_m) * *m / length
+                        } else {
+                            from_m
+                        };
+                    }
+                }
+            } else {
+                for m in measures.iter_mut() {
+                    *m *= factor;
+                }
+            }
+
+            let mut sfg = ShapefileGeometry::new(output_type);
+            for part in 0..record.num_parts as usize {
+                let part_start = record.parts[part] as usize;
+                let part_end = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize
+                } else {
+                    record.num_points as usize
+                };
+                if output_type == ShapeType::PolyLineZ {
+                    sfg.add_partz(
+                        &record.points[part_start..part_end],
+                        &measures[part_start..part_end],
+                        &record.z_array[part_start..part_end],
+                    );
+                } else {
+                    sfg.add_partm(
+                        &record.points[part_start..part_end],
+                        &measures[part_start..part_end],
+                    );
+                }
+            }
+            output.add_record(sfg);
+            output
+                .attributes
+                .add_record(input.attributes.get_record(record_num), false);
+
+            reporter.check_cancelled()?;
+            if verbose {
+                progress =
+                    (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            if num_skipped > 0 {
+                println!(
+                    "Warning: {} routes were skipped because they were empty or lacked measure values.",
+                    num_skipped
+                );
+            }
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+fn field_to_f64(value: &FieldData) -> Option<f64> {
+    match value {
+        FieldData::Int(v) => Some(*v as f64),
+        FieldData::Real(v) => Some(*v),
+        _ => None,
+    }
+}
@@ -16,7 +16,6 @@ use crate::tools::*;
 use whitebox_vector::*;
 use kdtree::distance::squared_euclidean;
 use kdtree::KdTree;
-use num_cpus;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
 use std::env;
@@ -24,7 +23,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 // const EPSILON: f64 = 0.000000001f64; //std::f64::EPSILON;
 const EPSILON: f64 = std::f64::EPSILON;
@@ -157,50 +156,15 @@ impl WhiteboxTool for Clip {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut clip_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut clip_file = parsed.get_string("clip").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val.contains("-input") {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-clip" {
-                clip_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -217,15 +181,9 @@ impl WhiteboxTool for Clip {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !clip_file.contains(&sep) && !clip_file.contains("/") {
-            clip_file = format!("{}{}", working_directory, clip_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        clip_file = resolve_input_path(&clip_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -248,6 +206,7 @@ impl WhiteboxTool for Clip {
         let mut first_point_in_part: usize;
         let mut last_point_in_part: usize;
         for record_num in 0..clip.num_records {
+            reporter.check_cancelled()?;
             let record = clip.get_record(record_num);
             for part in 0..record.num_parts as usize {
                 first_point_in_part = record.parts[part] as usize;
@@ -292,7 +251,7 @@ impl WhiteboxTool for Clip {
         let clip_bb = Arc::new(clip_bb);
         let is_clip_part_a_hole = Arc::new(is_clip_part_a_hole);
 
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
 
         match input.header.shape_type.base_shape_type() {
@@ -304,7 +263,7 @@ impl WhiteboxTool for Clip {
                     let clip_polylines = clip_polylines.clone();
                     let is_clip_part_a_hole = is_clip_part_a_hole.clone();
                     let tx = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         let mut p: Point2D;
                         let mut out: bool;
                         for record_num in (0..input.num_records).filter(|r| r % num_procs == tid) {
@@ -322,7 +281,9 @@ impl WhiteboxTool for Clip {
                                     }
                                 }
                             }
-                            tx.send((record_num, out)).unwrap();
+                            if tx.send((record_num, out)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -333,10 +294,11 @@ impl WhiteboxTool for Clip {
                     if out {
                         output_feature[record_num] = true;
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * r as f64 / (input.num_records - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -359,10 +321,11 @@ impl WhiteboxTool for Clip {
                                 .add_record(input.attributes.get_record(r).clone(), false)
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * r as f64 / (input.num_records - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -381,7 +344,7 @@ impl WhiteboxTool for Clip {
                         let clip_polylines = clip_polylines.clone();
                         let is_clip_part_a_hole = is_clip_part_a_hole.clone();
                         let tx = tx.clone();
-                        thread::spawn(move || {
+                        spawn(move || {
                             let mut p: Point2D;
                             let mut out: bool;
                             for point_num in (0..num_points).filter(|r| r % num_procs == tid) {
@@ -398,7 +361,9 @@ impl WhiteboxTool for Clip {
                                         }
                                     }
                                 }
-                                tx.send((point_num, out)).unwrap();
+                                if tx.send((point_num, out)).is_err() {
+                                    return;
+                                }
                             }
                         });
                     }
@@ -457,11 +422,12 @@ impl WhiteboxTool for Clip {
                         }
                     }
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * record_num as f64 / (input.num_records - 1) as f64)
                             as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -477,6 +443,7 @@ impl WhiteboxTool for Clip {
                 let mut features_bb: Vec<BoundingBox> = vec![];
                 let mut features_polylines: Vec<Polyline> = vec![];
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -501,6 +468,7 @@ impl WhiteboxTool for Clip {
 
                 // hunt for intersections in the overlapping bounding boxes
                 for record_num1 in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     for record_num2 in 0..clip_polylines.len() {
                         if features_bb[record_num1].overlaps(clip_bb[record_num2]) {
                             // find any intersections between the polylines
@@ -557,12 +525,13 @@ impl WhiteboxTool for Clip {
                         }
                     }
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (record_num1 + 1) as f64
                             / features_polylines.len() as f64)
                             as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -1625,11 +1594,12 @@ impl WhiteboxTool for Clip {
                         // }
                     }
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64)
                             as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -1647,6 +1617,7 @@ impl WhiteboxTool for Clip {
         let mut polygons: Vec<Polyline> = vec![];
         let mut features_bb: Vec<BoundingBox> = vec![];
         for record_num in 0..input.num_records {
+            reporter.check_cancelled()?;
             let record = input.get_record(record_num);
             for part in 0..record.num_parts as usize {
                 first_point_in_part = record.parts[part] as usize;
@@ -1769,16 +1740,18 @@ impl WhiteboxTool for Clip {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / features_polylines2.len() as f64)
                     as usize;
                 if progress != old_progress {
-                    println!("Searching for duplicate lines: {}%", progress);
+                    reporter.progress("Searching for duplicate lines", progress);
                     old_progress = progress;
                 }
             }
         }
         for i in (0..features_polylines2.len()).rev() {
+            reporter.check_cancelled()?;
             if duplicate[i] {
                 features_polylines2.remove(i);
             }
@@ -1789,6 +1762,7 @@ impl WhiteboxTool for Clip {
         output.header.shape_type = ShapeType::PolyLine;
         let mut fid = 1i32;
         for i in 0..features_polylines2.len() {
+            reporter.check_cancelled()?;
             // output the polygon
             let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
             sfg.add_part(&(features_polylines2[i].vertices));
@@ -1841,12 +1815,13 @@ impl WhiteboxTool for Clip {
         }
         }
 
+        reporter.check_cancelled()?;
         if verbose {
         progress = (100.0_f64 * (record_num1 + 1) as f64
         / features_polylines2.len() as f64)
         as usize;
         if progress != old_progress {
-        println!("Finding line intersections: {}%", progress);
+        reporter.progress("Finding line intersections", progress);
         old_progress = progress;
         }
         }
@@ -1862,10 +1837,11 @@ impl WhiteboxTool for Clip {
         }
         }
         }
+        reporter.check_cancelled()?;
         if verbose {
         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
         if progress != old_progress {
-        println!("Searching for duplicate lines: {}%", progress);
+        reporter.progress("Searching for duplicate lines", progress);
         old_progress = progress;
         }
         }
@@ -1976,10 +1952,11 @@ impl WhiteboxTool for Clip {
         }
         }
 
+        reporter.check_cancelled()?;
         if verbose {
         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
         if progress != old_progress {
-        println!("Finding node vertices: {}%", progress);
+        reporter.progress("Finding node vertices", progress);
         old_progress = progress;
         }
         }
@@ -2045,10 +2022,11 @@ impl WhiteboxTool for Clip {
         }
         }
 
+        reporter.check_cancelled()?;
         if verbose {
         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
         if progress != old_progress {
-        println!("Finding acyclic arcs: {}%", progress);
+        reporter.progress("Finding acyclic arcs", progress);
         old_progress = progress;
         }
         }
@@ -2500,10 +2478,11 @@ impl WhiteboxTool for Clip {
         }
         }
 
+        reporter.check_cancelled()?;
         if verbose {
         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
         if progress != old_progress {
-        println!("Finding polygons: {}%", progress);
+        reporter.progress("Finding polygons", progress);
         old_progress = progress;
         }
         }
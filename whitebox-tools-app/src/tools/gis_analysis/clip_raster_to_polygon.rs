@@ -2,16 +2,14 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 25/04/2018
-Last Modified: 16/03/2022
+Last Modified: 16/10/2026
 License: MIT
 
 NOTES: This tool differs from the Whitebox GAT tool in that it only takes a single raster input.
 */
 
-use whitebox_common::algorithms::point_in_poly;
+use super::raster_mask::{apply_polygon_mask, feature_file_name, MaskOptions};
 use whitebox_raster::*;
-use whitebox_common::structures::BoundingBox;
-use whitebox_common::structures::Point2D;
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
 use std::env;
@@ -27,6 +25,15 @@ use std::path;
 /// `--maintain_dimensions` parameter is used, in which case the output grid extent will match that of the input raster.
 /// The grid resolution of output raster is the same as the input raster.
 ///
+/// The clip mask may be grown or shrunk by a distance, in map units, using the `--buffer` parameter, e.g.
+/// `--buffer=50.0` retains all cells within 50 map units of a polygon while `--buffer=-50.0` removes a 50-unit
+/// margin from the inside of each polygon. The `--inverse` flag reverses the sense of the mask, such that cells
+/// inside of the (buffered) polygons are assigned NoData and those outside are retained; this is equivalent to
+/// the `ErasePolygonFromRaster` tool and always maintains the input raster dimensions. When the `--split_features`
+/// flag is specified, each polygon feature is used to clip the input separately and one output raster is created
+/// per feature, named by appending the one-based feature number to the output file name (e.g. `output_1.tif`,
+/// `output_2.tif`, ...).
+///
 /// It is very important that the input raster and the input vector polygon file share the same projection. The result
 /// is unlikely to be satisfactory otherwise.
 ///
@@ -59,7 +66,7 @@ impl ClipRasterToPolygon {
 
         parameters.push(ToolParameter {
             name: "Input Vector Polygon File".to_owned(),
-            flags: vec!["--polygons".to_owned()],
+            flags: vec!["--polygon".to_owned(), "--polygons".to_owned()],
             description: "Input vector polygons file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
                 VectorGeometryType::Polygon,
@@ -82,7 +89,34 @@ impl ClipRasterToPolygon {
             flags: vec!["--maintain_dimensions".to_owned()],
             description: "Maintain input raster dimensions?".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Buffer Distance".to_owned(),
+            flags: vec!["--buffer".to_owned()],
+            description: "Distance by which the clip mask is grown (positive) or shrunk (negative), in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Inverse mask?".to_owned(),
+            flags: vec!["--inverse".to_owned()],
+            description: "Retain cells outside of the polygons rather than inside?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Create one output per polygon feature?".to_owned(),
+            flags: vec!["--split_features".to_owned()],
+            description: "Clip the input separately to each polygon feature, creating one output per feature?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -144,52 +178,16 @@ impl WhiteboxTool for ClipRasterToPolygon {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut polygons_file = String::new();
-        let mut output_file = String::new();
-        let mut maintain_dimensions = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-polygon" || flag_val == "-polygons" {
-                polygons_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-maintain_dimensions" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    maintain_dimensions = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut polygons_file = parsed.get_string("polygons").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let maintain_dimensions = parsed.get_bool("maintain_dimensions");
+        let buffer = parsed.get_f64("buffer").unwrap_or(0f64);
+        let inverse = parsed.get_bool("inverse");
+        let split_features = parsed.get_bool("split_features");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -202,20 +200,9 @@ impl WhiteboxTool for ClipRasterToPolygon {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !polygons_file.contains(&sep) && !polygons_file.contains("/") {
-            polygons_file = format!("{}{}", working_directory, polygons_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        polygons_file = resolve_input_path(&polygons_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -223,13 +210,10 @@ impl WhiteboxTool for ClipRasterToPolygon {
         let input = Raster::new(&input_file, "r")?;
 
         let start = Instant::now();
-        let rows = input.configs.rows as isize;
-        let columns = input.configs.columns as isize;
-        let nodata = input.configs.nodata;
 
         let polygons = Shapefile::read(&polygons_file)?;
 
-        // make sure the input vector file is of points type
+        // make sure the input vector file is of polygons type
         if polygons.header.shape_type.base_shape_type() != ShapeType::Polygon {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
@@ -237,379 +221,55 @@ impl WhiteboxTool for ClipRasterToPolygon {
             ));
         }
 
-        if maintain_dimensions {
-            // Output raster has same dimensions as the input
-            let mut output = Raster::initialize_using_file(&output_file, &input);
-
-            let mut start_point_in_part: usize;
-            let mut end_point_in_part: usize;
-            let (mut row, mut col): (isize, isize);
-            let (mut x, mut y): (f64, f64);
-            let (mut starting_row, mut ending_row, mut starting_col, mut ending_col): (
-                isize,
-                isize,
-                isize,
-                isize,
-            );
-            let num_records = polygons.num_records;
+        let options = MaskOptions {
+            inverse: inverse,
+            buffer: buffer,
+            crop: !maintain_dimensions,
+        };
 
+        // Either a single mask formed by all of the polygons, or one mask per feature.
+        let mut jobs: Vec<(String, Vec<usize>)> = vec![];
+        if split_features {
             for record_num in 0..polygons.num_records {
-                let record = polygons.get_record(record_num);
-
-                let mut part_num = 1;
-                for part in 0..record.num_parts as usize {
-                    if !record.is_hole(part as i32) {
-                        // Add these cells in
-
-                        start_point_in_part = record.parts[part] as usize;
-                        end_point_in_part = if part < record.num_parts as usize - 1 {
-                            record.parts[part + 1] as usize - 1
-                        } else {
-                            record.num_points as usize - 1
-                        };
-
-                        // First, figure out the minimum and maximum row and column for the polygon part
-                        starting_row = rows;
-                        ending_row = 0;
-                        starting_col = columns;
-                        ending_col = 0;
-                        for p in start_point_in_part..end_point_in_part + 1 {
-                            row = input.get_row_from_y(record.points[p].y);
-                            col = input.get_column_from_x(record.points[p].x);
-                            if row < starting_row {
-                                starting_row = row;
-                            }
-                            if row > ending_row {
-                                ending_row = row;
-                            }
-                            if col < starting_col {
-                                starting_col = col;
-                            }
-                            if col > ending_col {
-                                ending_col = col;
-                            }
-                        }
-
-                        for r in starting_row..ending_row {
-                            y = input.get_y_from_row(r);
-                            for c in starting_col..ending_col {
-                                x = input.get_x_from_column(c);
-
-                                if point_in_poly(
-                                    &Point2D { x: x, y: y },
-                                    &record.points[start_point_in_part..end_point_in_part + 1],
-                                ) {
-                                    output.set_value(r, c, input.get_value(r, c));
-                                }
-                            }
-                            if verbose {
-                                progress = (100.0_f64 * (r - starting_row) as f64
-                                    / (ending_row - starting_row) as f64)
-                                    as usize;
-                                if progress != old_progress {
-                                    println!(
-                                        "Progress (rec {} of {} part {}): {}%",
-                                        record_num + 1,
-                                        num_records,
-                                        part_num,
-                                        progress
-                                    );
-                                    old_progress = progress;
-                                }
-                            }
-                        }
-                        part_num += 1;
-                    }
-                }
-
-                for part in 0..record.num_parts as usize {
-                    if record.is_hole(part as i32) {
-                        // Erase these cells
-
-                        start_point_in_part = record.parts[part] as usize;
-                        end_point_in_part = if part < record.num_parts as usize - 1 {
-                            record.parts[part + 1] as usize - 1
-                        } else {
-                            record.num_points as usize - 1
-                        };
-
-                        // First, figure out the minimum and maximum row and column for the polygon part
-                        starting_row = rows;
-                        ending_row = 0;
-                        starting_col = columns;
-                        ending_col = 0;
-                        for p in start_point_in_part..end_point_in_part + 1 {
-                            row = input.get_row_from_y(record.points[p].y);
-                            col = input.get_column_from_x(record.points[p].x);
-                            if row < starting_row {
-                                starting_row = row;
-                            }
-                            if row > ending_row {
-                                ending_row = row;
-                            }
-                            if col < starting_col {
-                                starting_col = col;
-                            }
-                            if col > ending_col {
-                                ending_col = col;
-                            }
-                        }
-
-                        for r in starting_row..ending_row {
-                            y = input.get_y_from_row(r);
-                            for c in starting_col..ending_col {
-                                x = input.get_x_from_column(c);
-                                if point_in_poly(
-                                    &Point2D { x: x, y: y },
-                                    &record.points[start_point_in_part..end_point_in_part + 1],
-                                ) {
-                                    output.set_value(r, c, nodata);
-                                }
-                            }
-                            if verbose {
-                                progress = (100.0_f64 * (r - starting_row) as f64
-                                    / (ending_row - starting_row) as f64)
-                                    as usize;
-                                if progress != old_progress {
-                                    println!(
-                                        "Progress (rec {} of {} part {}): {}%",
-                                        record_num + 1,
-                                        num_records,
-                                        part_num,
-                                        progress
-                                    );
-                                    old_progress = progress;
-                                }
-                            }
-                        }
-                        part_num += 1;
-                    }
+                reporter.check_cancelled()?;
+                if polygons.get_record(record_num).num_points > 0 {
+                    jobs.push((
+                        feature_file_name(&output_file, record_num + 1),
+                        vec![record_num],
+                    ));
                 }
             }
-
-            let elapsed_time = get_formatted_elapsed_time(start);
-            output.add_metadata_entry(format!(
-                "Created by whitebox_tools\' {} tool",
-                self.get_tool_name()
-            ));
-            output.add_metadata_entry(format!("Input file: {}", input_file));
-            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
-
-            if verbose {
-                println!("Saving data...")
-            };
-            let _ = match output.write() {
-                Ok(_) => {
-                    if verbose {
-                        println!("Output file written")
-                    }
-                }
-                Err(e) => return Err(e),
-            };
-
-            if verbose {
-                println!(
-                    "{}",
-                    &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
-                );
-            }
         } else {
-            // we'll need to trim the raster to the extent of the polygons.
-            let vec_bb = BoundingBox::new(
-                polygons.header.x_min,
-                polygons.header.x_max,
-                polygons.header.y_min,
-                polygons.header.y_max,
-            );
-            let mut rast_bb = BoundingBox::new(
-                input.configs.west,
-                input.configs.east,
-                input.configs.south,
-                input.configs.north,
-            );
-            rast_bb.contract_to(vec_bb);
-
-            let west: f64 = rast_bb.min_x;
-            let north: f64 = rast_bb.max_y;
-            let rows: isize =
-                (((north - rast_bb.min_y) / input.configs.resolution_y).ceil()) as isize;
-            let columns: isize =
-                (((rast_bb.max_x - west) / input.configs.resolution_x).ceil()) as isize;
-            let south: f64 = north - rows as f64 * input.configs.resolution_y;
-            let east = west + columns as f64 * input.configs.resolution_x;
-
-            if rows > 500_000 || columns > 500_000 {
-                return Err(Error::new(
-                    ErrorKind::InvalidInput,
-                    "The output rasters dimensions are too big. This is may be due to a projection inconsistency between the input raster and the polygon file.",
-                ));
-            }
-
-            let mut configs = RasterConfigs {
-                ..Default::default()
-            };
-            configs.rows = rows as usize;
-            configs.columns = columns as usize;
-            configs.north = north;
-            configs.south = south;
-            configs.east = east;
-            configs.west = west;
-            configs.resolution_x = input.configs.resolution_x;
-            configs.resolution_y = input.configs.resolution_y;
-            configs.nodata = nodata;
-            configs.data_type = input.configs.data_type;
-            configs.photometric_interp = input.configs.photometric_interp;
-            configs.palette = input.configs.palette.clone();
-
-            let mut output = Raster::initialize_using_config(&output_file, &configs);
-
-            let mut start_point_in_part: usize;
-            let mut end_point_in_part: usize;
-            let (mut row, mut col): (isize, isize);
-            let (mut row_in, mut col_in): (isize, isize);
-            let (mut x, mut y): (f64, f64);
-            let (mut starting_row, mut ending_row, mut starting_col, mut ending_col): (
-                isize,
-                isize,
-                isize,
-                isize,
-            );
-            let num_records = polygons.num_records;
-            let mut part_num: i32;
-            for record_num in 0..polygons.num_records {
-                let record = polygons.get_record(record_num);
-
-                part_num = 1;
-                for part in 0..record.num_parts as usize {
-                    if !record.is_hole(part as i32) {
-                        // Add these cells in
-
-                        start_point_in_part = record.parts[part] as usize;
-                        end_point_in_part = if part < record.num_parts as usize - 1 {
-                            record.parts[part + 1] as usize - 1
-                        } else {
-                            record.num_points as usize - 1
-                        };
-
-                        // First, figure out the minimum and maximum row and column for the polygon part
-                        starting_row = rows;
-                        ending_row = 0;
-                        starting_col = columns;
-                        ending_col = 0;
-                        for p in start_point_in_part..end_point_in_part + 1 {
-                            row = output.get_row_from_y(record.points[p].y);
-                            col = output.get_column_from_x(record.points[p].x);
-                            if row < starting_row {
-                                starting_row = row;
-                            }
-                            if row > ending_row {
-                                ending_row = row;
-                            }
-                            if col < starting_col {
-                                starting_col = col;
-                            }
-                            if col > ending_col {
-                                ending_col = col;
-                            }
-                        }
-
-                        for r in starting_row..ending_row {
-                            y = output.get_y_from_row(r);
-                            for c in starting_col..ending_col {
-                                x = output.get_x_from_column(c);
-                                if point_in_poly(
-                                    &Point2D { x: x, y: y },
-                                    &record.points[start_point_in_part..end_point_in_part + 1],
-                                ) {
-                                    row_in = input.get_row_from_y(y);
-                                    col_in = input.get_column_from_x(x);
-                                    output.set_value(r, c, input.get_value(row_in, col_in));
-                                }
-                            }
-                            if verbose {
-                                progress = (100.0_f64 * (r - starting_row) as f64
-                                    / (ending_row - starting_row) as f64)
-                                    as usize;
-                                if progress != old_progress {
-                                    println!(
-                                        "Progress (rec {} of {} part {}): {}%",
-                                        record_num + 1,
-                                        num_records,
-                                        part_num,
-                                        progress
-                                    );
-                                    old_progress = progress;
-                                }
-                            }
-                        }
-                        part_num += 1;
-                    }
-                }
-
-                for part in 0..record.num_parts as usize {
-                    if record.is_hole(part as i32) {
-                        // Erase these cells
-
-                        start_point_in_part = record.parts[part] as usize;
-                        end_point_in_part = if part < record.num_parts as usize - 1 {
-                            record.parts[part + 1] as usize - 1
-                        } else {
-                            record.num_points as usize - 1
-                        };
-
-                        // First, figure out the minimum and maximum row and column for the polygon part
-                        starting_row = rows;
-                        ending_row = 0;
-                        starting_col = columns;
-                        ending_col = 0;
-                        for p in start_point_in_part..end_point_in_part + 1 {
-                            row = output.get_row_from_y(record.points[p].y);
-                            col = output.get_column_from_x(record.points[p].x);
-                            if row < starting_row {
-                                starting_row = row;
-                            }
-                            if row > ending_row {
-                                ending_row = row;
-                            }
-                            if col < starting_col {
-                                starting_col = col;
-                            }
-                            if col > ending_col {
-                                ending_col = col;
-                            }
-                        }
+            jobs.push((output_file.clone(), (0..polygons.num_records).collect()));
+        }
 
-                        for r in starting_row..ending_row {
-                            y = output.get_y_from_row(r);
-                            for c in starting_col..ending_col {
-                                x = output.get_x_from_column(c);
-                                if point_in_poly(
-                                    &Point2D { x: x, y: y },
-                                    &record.points[start_point_in_part..end_point_in_part + 1],
-                                ) {
-                                    output.set_value(r, c, nodata);
-                                }
-                            }
-                            if verbose {
-                                progress = (100.0_f64 * (r - starting_row) as f64
-                                    / (ending_row - starting_row) as f64)
-                                    as usize;
-                                if progress != old_progress {
-                                    println!(
-                                        "Progress (rec {} of {} part {}): {}%",
-                                        record_num + 1,
-                                        num_records,
-                                        part_num,
-                                        progress
-                                    );
-                                    old_progress = progress;
-                                }
-                            }
+        let num_jobs = jobs.len();
+        for (job_num, (file_name, records)) in jobs.iter().enumerate() {
+            reporter.check_cancelled()?;
+            if verbose && num_jobs > 1 {
+                println!("Clipping feature {} of {}...", job_num + 1, num_jobs);
+            }
+            let mut output = match apply_polygon_mask(
+                &input,
+                &polygons,
+                records,
+                &options,
+                file_name,
+                verbose && num_jobs == 1,
+                reporter,
+            ) {
+                Ok(r) => r,
+                Err(e) => {
+                    if split_features {
+                        // A feature that falls entirely off of the raster produces no output.
+                        if verbose {
+                            println!("Warning: {}", e);
                         }
-                        part_num += 1;
+                        continue;
                     }
+                    return Err(e);
                 }
-            }
+            };
 
             let elapsed_time = get_formatted_elapsed_time(start);
             output.add_metadata_entry(format!(
@@ -617,26 +277,34 @@ impl WhiteboxTool for ClipRasterToPolygon {
                 self.get_tool_name()
             ));
             output.add_metadata_entry(format!("Input file: {}", input_file));
+            output.add_metadata_entry(format!("Polygons file: {}", polygons_file));
+            if buffer != 0f64 {
+                output.add_metadata_entry(format!("Buffer distance: {}", buffer));
+            }
+            if inverse {
+                output.add_metadata_entry("Inverse mask: true".to_string());
+            }
             output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
-            if verbose {
+            if verbose && num_jobs == 1 {
                 println!("Saving data...")
             };
             let _ = match output.write() {
                 Ok(_) => {
-                    if verbose {
+                    if verbose && num_jobs == 1 {
                         println!("Output file written")
                     }
                 }
                 Err(e) => return Err(e),
             };
+        }
 
-            if verbose {
-                println!(
-                    "{}",
-                    &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
-                );
-            }
+        if verbose {
+            let elapsed_time = get_formatted_elapsed_time(start);
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
         }
 
         Ok(())
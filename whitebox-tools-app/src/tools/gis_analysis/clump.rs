@@ -10,7 +10,7 @@ use whitebox_raster::*;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool re-categorizes data in a raster image by grouping cells that form  
@@ -68,7 +68,7 @@ impl Clump {
             description: "Flag indicating whether diagonal connections should be considered."
                 .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: false,
         });
 
@@ -144,50 +144,13 @@ impl WhiteboxTool for Clump {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut diag = false;
-        let mut zero_back = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-diag" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    diag = true;
-                }
-            } else if flag_val == "-zero_back" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    zero_back = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let diag = parsed.get_bool("diag");
+        let zero_back = parsed.get_bool("zero_back");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -200,17 +163,11 @@ impl WhiteboxTool for Clump {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -254,7 +211,7 @@ impl WhiteboxTool for Clump {
             for col in 0..columns {
                 zin = input[(row, col)];
                 zout = output[(row, col)];
-                if zin != nodata && zin != back_val && zout == out_nodata {
+                if !is_nodata(zin, nodata) && zin != back_val && is_nodata(zout, out_nodata) {
                     fid += 1f64;
                     output[(row, col)] = fid;
                     num_solved_cells += 1;
@@ -267,12 +224,13 @@ impl WhiteboxTool for Clump {
                         count += 1;
                         if count == 1000 {
                             count = 0;
+                            reporter.check_cancelled()?;
                             if verbose {
                                 progress = (100.0_f64 * num_solved_cells as f64
                                     / (num_cells - 1) as f64)
                                     as usize;
                                 if progress != old_progress {
-                                    println!("Performing analysis: {}%", progress);
+                                    reporter.progress("Performing analysis", progress);
                                     old_progress = progress;
                                 }
                             }
@@ -280,24 +238,25 @@ impl WhiteboxTool for Clump {
                         for i in 0..num_neighbours {
                             zn = input[(r + dy[i], c + dx[i])];
                             zout = output[(r + dy[i], c + dx[i])];
-                            if zn == zin && zout == out_nodata {
+                            if zn == zin && is_nodata(zout, out_nodata) {
                                 output[(r + dy[i], c + dx[i])] = fid;
                                 num_solved_cells += 1;
                                 stack.push((r + dy[i], c + dx[i]));
                             }
                         }
                     }
-                } else if zin == nodata {
+                } else if is_nodata(zin, nodata) {
                     num_solved_cells += 1;
                 } else if zin == back_val {
                     num_solved_cells += 1;
                     output[(row, col)] = back_val;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    reporter.progress("Performing analysis", progress);
                     old_progress = progress;
                 }
             }
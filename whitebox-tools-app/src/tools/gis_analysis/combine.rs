@@ -0,0 +1,334 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use whitebox_common::structures::Array2D;
+use crate::tools::*;
+use std::collections::HashMap;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool assigns a unique identifier to each unique combination of values across a group of
+/// categorical input rasters (`--inputs`). For example, combining a land-cover raster with a soil type
+/// raster and a slope class raster will produce an output raster in which each grid cell is assigned the
+/// ID of its land-cover/soil/slope combination. This is a fundamental overlay operation for the creation
+/// of, for example, hydrologic response units (HRUs) and habitat suitability classes.
+///
+/// The input values are rounded to the nearest integer. IDs are assigned sequentially, starting at 1, in
+/// the order in which the combinations are first encountered during a row-by-row scan of the rasters.
+/// Grid cells that are nodata in any of the inputs are assigned nodata in the output. Each of the input
+/// rasters must share the same number of rows and columns and spatial extent.
+///
+/// In addition to the output raster (`--output`), the tool writes a cross-reference table, in CSV format,
+/// that relates each ID to its input values, the number of grid cells (COUNT), and the area (AREA) of the
+/// combination, in the squared map units of the input rasters. The table plays the role of a raster
+/// attribute table (RAT) for the output. By default, the table is saved alongside the output raster with
+/// the same name and a *.csv* extension; an alternative file name may be specified using `--table`.
+///
+/// # See Also
+/// `CrossTabulation`, `Reclass`, `Clump`
+pub struct Combine {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl Combine {
+    pub fn new() -> Combine {
+        // public constructor
+        let name = "Combine".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description =
+            "Assigns a unique ID to each unique combination of values in a group of categorical rasters."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--inputs".to_owned()],
+            description: "Input categorical raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Table File (optional)".to_owned(),
+            flags: vec!["--table".to_owned()],
+            description: "Output cross-reference table (CSV) file; by default, the output raster name with a .csv extension.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd='*path*to*data*' -i='landcover.tif;soils.tif;slope_class.tif' -o=hrus.tif --table=hrus.csv", short_exe, name).replace("*", &sep);
+
+        Combine {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for Combine {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut table_file = parsed.get_string("table").unwrap_or_default();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        output_file = resolve_path(&output_file, working_directory);
+        if table_file.is_empty() {
+            table_file = match path::Path::new(&output_file).extension() {
+                Some(ext) => {
+                    let ext = ext.to_str().unwrap_or("");
+                    format!("{}.csv", &output_file[..output_file.len() - ext.len() - 1])
+                }
+                None => format!("{}.csv", output_file),
+            };
+        } else {
+            table_file = resolve_path(&table_file, working_directory);
+        }
+
+        let mut cmd = input_files.split(";");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = input_files.split(",");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+        let file_names: Vec<String> = vec
+            .iter()
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .map(|v| resolve_path(v, working_directory))
+            .collect();
+        let num_files = file_names.len();
+        if num_files < 2 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                "There is something incorrect with the input files. At least two inputs are required to operate this tool."));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let mut inputs = Vec::with_capacity(num_files);
+        for input_file in &file_names {
+            let input = Raster::new(input_file, "r")?;
+            if inputs.len() > 0 {
+                let first: &Raster = &inputs[0];
+                if input.configs.rows != first.configs.rows
+                    || input.configs.columns != first.configs.columns
+                {
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
+                }
+                warn_if_crs_differs(&input, &first, verbose);
+            }
+            inputs.push(input);
+        }
+
+        let start = Instant::now();
+
+        let rows = inputs[0].configs.rows as isize;
+        let columns = inputs[0].configs.columns as isize;
+        let nodatas: Vec<f64> = inputs.iter().map(|r| r.configs.nodata).collect();
+        let out_nodata = -32768f64;
+        let mut ids: Array2D<i32> = Array2D::new(rows, columns, -32768i32, -32768i32)?;
+
+        let mut lookup: HashMap<Vec<i64>, i32> = HashMap::new();
+        let mut combinations: Vec<Vec<i64>> = vec![];
+        let mut counts: Vec<usize> = vec![];
+        let mut key = vec![0i64; num_files];
+        for row in 0..rows {
+            'col: for col in 0..columns {
+                for a in 0..num_files {
+                    let z = inputs[a].get_value(row, col);
+                    if is_nodata(z, nodatas[a]) {
+                        continue 'col;
+                    }
+                    key[a] = z.round() as i64;
+                }
+                let id = match lookup.get(&key) {
+                    Some(&id) => id,
+                    None => {
+                        combinations.push(key.clone());
+                        counts.push(0);
+                        let id = combinations.len() as i32;
+                        lookup.insert(key.clone(), id);
+                        id
+                    }
+                };
+                counts[id as usize - 1] += 1;
+                ids.set_value(row, col, id);
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &inputs[0]);
+        output.configs.nodata = out_nodata;
+        output.configs.data_type = DataType::I32;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        for row in 0..rows {
+            output.set_row_data(row, ids.get_row_data(row).iter().map(|&v| v as f64).collect());
+        }
+        let cell_area = inputs[0].configs.resolution_x * inputs[0].configs.resolution_y;
+        drop(inputs);
+
+        if verbose {
+            println!("Number of unique combinations: {}", combinations.len());
+        }
+
+        // write the cross-reference table
+        let f = File::create(&table_file)?;
+        let mut writer = BufWriter::new(f);
+        let mut header = String::from("ID");
+        for input_file in &file_names {
+            let stem = path::Path::new(input_file)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("INPUT")
+                .replace(",", "_");
+            header.push_str(&format!(",{}", stem));
+        }
+        header.push_str(",COUNT,AREA\n");
+        writer.write_all(header.as_bytes())?;
+        for i in 0..combinations.len() {
+            let mut line = format!("{}", i + 1);
+            for v in &combinations[i] {
+                line.push_str(&format!(",{}", v));
+            }
+            line.push_str(&format!(",{},{}\n", counts[i], counts[i] as f64 * cell_area));
+            writer.write_all(line.as_bytes())?;
+        }
+        let _ = writer.flush();
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        for input_file in &file_names {
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+        }
+        output.add_metadata_entry(format!("Cross-reference table: {}", table_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
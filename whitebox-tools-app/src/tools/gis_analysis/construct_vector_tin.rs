@@ -172,72 +172,27 @@ impl WhiteboxTool for ConstructVectorTIN {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
         let mut field_name = String::new();
-        let mut use_z = false;
+        let use_z = parsed.get_bool("use_z");
         let mut use_field = false;
-        let mut output_file: String = "".to_string();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
         let mut max_triangle_edge_length = f64::INFINITY;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_string("field") {
+            field_name = value.to_string();
+            use_field = true;
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                field_name = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                use_field = true;
-            } else if flag_val.contains("use_z") {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    use_z = true;
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-max_triangle_edge_length" {
-                max_triangle_edge_length = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
+        if let Some(value) = parsed.get_f64("max_triangle_edge_length") {
+            max_triangle_edge_length = value;
 
-                max_triangle_edge_length *= max_triangle_edge_length; // actually squared distance
-            }
+            max_triangle_edge_length *= max_triangle_edge_length; // actually squared distance
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -254,13 +209,9 @@ impl WhiteboxTool for ConstructVectorTIN {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Shapefile::read(&input_file)?;
 
@@ -359,11 +310,12 @@ impl WhiteboxTool for ConstructVectorTIN {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Reading points: {}%", progress);
+                    reporter.progress("Reading points", progress);
                     old_progress = progress;
                 }
             }
@@ -455,10 +407,11 @@ impl WhiteboxTool for ConstructVectorTIN {
                 rec_num += 1i32;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / (result.triangles.len() - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Creating polygons: {}%", progress);
+                    reporter.progress("Creating polygons", progress);
                     old_progress = progress;
                 }
             }
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 04/072017
-Last Modified: 13/10/2018
+Last Modified: 16/10/2026
 License: MIT
 
 NOTES: This tool is essentially the same as the watershed tool in functionality.
@@ -13,7 +13,7 @@ use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to identify the 'catchment area' of each source grid cell in a
@@ -27,6 +27,14 @@ use std::path;
 /// NoData values in the input *back-link* image are assigned NoData values in the output
 /// image.
 ///
+/// When the *back-link* raster is an uncompressed, single-band GeoTIFF, it is read through a
+/// memory map rather than being decoded into memory, which substantially reduces the tool's peak
+/// memory use for large inputs.
+///
+/// The source and back-link rasters must share the same grid. If `--align=auto` is specified, a
+/// source raster that differs in extent or resolution is instead resampled and cropped to the grid
+/// of the back-link raster, using nearest-neighbour interpolation, with a warning.
+///
 /// # See Also
 /// `CostDistance`, `CostPathway`, `EuclideanAllocation`
 pub struct CostAllocation {
@@ -73,6 +81,15 @@ impl CostAllocation {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Input Alignment".to_owned(),
+            flags: vec!["--align".to_owned()],
+            description: "Input alignment mode; 'auto' resamples the source raster to the grid of the back-link raster.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["none".to_owned(), "auto".to_owned()]),
+            default_value: Some("none".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let e = format!("{}", env::current_exe().unwrap().display());
         let mut parent = env::current_exe().unwrap();
@@ -96,6 +113,20 @@ impl CostAllocation {
             example_usage: usage,
         }
     }
+
+    /// Identifies the source to which each cell of an in-memory back-link raster, e.g. from
+    /// `CostDistance::execute`, is connected, without reading or writing any files. The source and
+    /// back-link rasters must share the same grid.
+    pub fn execute(source: &Raster, backlink: &Raster) -> Result<Raster, Error> {
+        check_same_grid(source, backlink)?;
+        allocate(
+            source,
+            |row, col| backlink.get_value(row, col),
+            backlink.configs.nodata,
+            false,
+            &ConsoleProgress,
+        )
+    }
 }
 
 impl WhiteboxTool for CostAllocation {
@@ -131,45 +162,16 @@ impl WhiteboxTool for CostAllocation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut d8_file = String::new();
-        let mut pourpts_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-backlink" || vec[0].to_lowercase() == "--backlink" {
-                if keyval {
-                    d8_file = vec[1].to_string();
-                } else {
-                    d8_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-source" || vec[0].to_lowercase() == "--source" {
-                if keyval {
-                    pourpts_file = vec[1].to_string();
-                } else {
-                    pourpts_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("backlink").unwrap_or_default();
+        let mut pourpts_file = parsed.get_string("source").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut align = false;
+
+        if let Some(value) = parsed.get_string("align") {
+            align = value.is_empty() || value.to_lowercase().contains("auto");
         }
 
         if verbose {
@@ -183,161 +185,50 @@ impl WhiteboxTool for CostAllocation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
-
-        if !d8_file.contains(&sep) && !d8_file.contains("/") {
-            d8_file = format!("{}{}", working_directory, d8_file);
-        }
-        if !pourpts_file.contains(&sep) && !pourpts_file.contains("/") {
-            pourpts_file = format!("{}{}", working_directory, pourpts_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        d8_file = resolve_input_path(&d8_file, working_directory)?;
+        pourpts_file = resolve_input_path(&pourpts_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
         };
 
-        let pntr = Raster::new(&d8_file, "r")?;
-        let pourpts = Raster::new(&pourpts_file, "r")?;
-
-        let start = Instant::now();
-
-        let rows = pntr.configs.rows as isize;
-        let columns = pntr.configs.columns as isize;
-        let nodata = pourpts.configs.nodata;
-        let pntr_nodata = pntr.configs.nodata;
-        let palette = pourpts.configs.palette.clone();
-
-        // make sure the input files have the same size
-        if pourpts.configs.rows != pntr.configs.rows
-            || pourpts.configs.columns != pntr.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
-
-        let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
-        let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
-
-        let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -2, -2)?;
-        let mut output = Raster::initialize_using_file(&output_file, &pourpts);
-        output.configs.palette = palette;
-        output.configs.photometric_interp = pourpts.configs.photometric_interp;
-        let low_value = f64::MIN;
-        output.reinitialize_values(low_value);
-
-        // Create a mapping from the pointer values to cells offsets.
-        // This may seem wasteful, using only 8 of 129 values in the array,
-        // but the mapping method is far faster than calculating z.ln() / ln(2.0).
-        // It's also a good way of allowing for different point styles.
-        let mut pntr_matches: [i8; 129] = [0i8; 129];
-        // This maps Whitebox-style D8 pointer values
-        // onto the cell offsets in d_x and d_y.
-        pntr_matches[1] = 0i8;
-        pntr_matches[2] = 1i8;
-        pntr_matches[4] = 2i8;
-        pntr_matches[8] = 3i8;
-        pntr_matches[16] = 4i8;
-        pntr_matches[32] = 5i8;
-        pntr_matches[64] = 6i8;
-        pntr_matches[128] = 7i8;
-
-        let mut z: f64;
-        for row in 0..rows {
-            for col in 0..columns {
-                z = pntr[(row, col)];
-                if z != pntr_nodata {
-                    if z > 0.0 {
-                        flow_dir[(row, col)] = pntr_matches[z as usize];
-                    } else {
-                        flow_dir[(row, col)] = -1i8;
-                    }
-                } else {
-                    output[(row, col)] = nodata;
-                }
-                z = pourpts[(row, col)];
-                if z != nodata && z > 0.0 {
-                    output[(row, col)] = z;
-                }
-            }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Initializing: {}%", progress);
-                    old_progress = progress;
-                }
-            }
-        }
-
-        let mut flag: bool;
-        let (mut x, mut y): (isize, isize);
-        let mut dir: i8;
-        let mut outlet_id: f64;
-        for row in 0..rows {
-            for col in 0..columns {
-                if output[(row, col)] == low_value {
-                    // && flow_dir[(row, col)] != -2i8 {
-                    flag = false;
-                    x = col;
-                    y = row;
-                    outlet_id = nodata;
-                    while !flag {
-                        // find its downslope neighbour
-                        dir = flow_dir[(y, x)];
-                        if dir >= 0 {
-                            // move x and y accordingly
-                            x += d_x[dir as usize];
-                            y += d_y[dir as usize];
-
-                            // if the new cell already has a value in the output, use that as the outletID
-                            z = output[(y, x)];
-                            if z != low_value {
-                                outlet_id = z;
-                                flag = true;
-                            }
-                        } else {
-                            flag = true;
-                        }
-                    }
-
-                    flag = false;
-                    x = col;
-                    y = row;
-                    output[(y, x)] = outlet_id;
-                    while !flag {
-                        // find its downslope neighbour
-                        dir = flow_dir[(y, x)];
-                        if dir >= 0 {
-                            // move x and y accordingly
-                            x += d_x[dir as usize];
-                            y += d_y[dir as usize];
-
-                            // if the new cell already has a value in the output, use that as the outletID
-                            if output[(y, x)] != low_value {
-                                flag = true;
-                            }
-                        } else {
-                            flag = true;
-                        }
-                        output[(y, x)] = outlet_id;
-                    }
-                }
-            }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
-                }
-            }
-        }
+        let start;
+        let mut output = if MappedRaster::can_map(&d8_file) {
+            // An uncompressed GeoTIFF back-link raster is read through a memory map rather
+            // than being decoded into memory, which roughly halves the memory used by the inputs.
+            let pntr = MappedRaster::open(&d8_file)?;
+            // make sure the input files have the same size
+            let pourpts = read_secondary_raster_on_grid(
+                &pourpts_file,
+                &pntr.configs,
+                &pntr.get_short_filename(),
+                align,
+                false,
+                verbose,
+            )?;
+            start = Instant::now();
+            allocate(
+                &pourpts,
+                |row, col| pntr.get_value(row, col),
+                pntr.configs.nodata,
+                verbose,
+                reporter,
+            )?
+        } else {
+            let pntr = Raster::new(&d8_file, "r")?;
+            // make sure the input files have the same size
+            let pourpts = read_secondary_raster(&pourpts_file, &pntr, align, false, verbose)?;
+            start = Instant::now();
+            allocate(
+                &pourpts,
+                |row, col| pntr.get_value(row, col),
+                pntr.configs.nodata,
+                verbose,
+                reporter,
+            )?
+        };
+        output.set_file_name(&output_file);
 
         let elapsed_time = get_formatted_elapsed_time(start);
         output.add_metadata_entry(format!(
@@ -370,3 +261,142 @@ impl WhiteboxTool for CostAllocation {
         Ok(())
     }
 }
+
+// Assigns each cell the value of the source cell at the end of its least-cost pathway. The
+// back-link values are read through `pntr_value`, so that the back-link raster may be held
+// either in memory or in a memory-mapped file; it must share the grid of `pourpts`.
+fn allocate<F: Fn(isize, isize) -> f64>(
+    pourpts: &Raster,
+    pntr_value: F,
+    pntr_nodata: f64,
+    verbose: bool,
+    reporter: &dyn ProgressReporter,
+) -> Result<Raster, Error> {
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+
+    let rows = pourpts.configs.rows as isize;
+    let columns = pourpts.configs.columns as isize;
+    let nodata = pourpts.configs.nodata;
+    let palette = pourpts.configs.palette.clone();
+
+    let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+    let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+    let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -2, -2)?;
+    let mut output = Raster::initialize_using_file("cost_allocation.tif", pourpts);
+    output.configs.palette = palette;
+    output.configs.photometric_interp = pourpts.configs.photometric_interp;
+    let low_value = f64::MIN;
+    output.reinitialize_values(low_value);
+
+    // Create a mapping from the pointer values to cells offsets.
+    // This may seem wasteful, using only 8 of 129 values in the array,
+    // but the mapping method is far faster than calculating z.ln() / ln(2.0).
+    // It's also a good way of allowing for different point styles.
+    let mut pntr_matches: [i8; 129] = [0i8; 129];
+    // This maps Whitebox-style D8 pointer values
+    // onto the cell offsets in d_x and d_y.
+    pntr_matches[1] = 0i8;
+    pntr_matches[2] = 1i8;
+    pntr_matches[4] = 2i8;
+    pntr_matches[8] = 3i8;
+    pntr_matches[16] = 4i8;
+    pntr_matches[32] = 5i8;
+    pntr_matches[64] = 6i8;
+    pntr_matches[128] = 7i8;
+
+    let mut z: f64;
+    for row in 0..rows {
+        for col in 0..columns {
+            z = pntr_value(row, col);
+            if !is_nodata(z, pntr_nodata) {
+                if z > 0.0 {
+                    flow_dir[(row, col)] = pntr_matches[z as usize];
+                } else {
+                    flow_dir[(row, col)] = -1i8;
+                }
+            } else {
+                output[(row, col)] = nodata;
+            }
+            z = pourpts[(row, col)];
+            if !is_nodata(z, nodata) && z > 0.0 {
+                output[(row, col)] = z;
+            }
+        }
+        reporter.check_cancelled()?;
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Initializing", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    let mut flag: bool;
+    let (mut x, mut y): (isize, isize);
+    let mut dir: i8;
+    let mut outlet_id: f64;
+    for row in 0..rows {
+        for col in 0..columns {
+            if output[(row, col)] == low_value {
+                // && flow_dir[(row, col)] != -2i8 {
+                flag = false;
+                x = col;
+                y = row;
+                outlet_id = nodata;
+                while !flag {
+                    // find its downslope neighbour
+                    dir = flow_dir[(y, x)];
+                    if dir >= 0 {
+                        // move x and y accordingly
+                        x += d_x[dir as usize];
+                        y += d_y[dir as usize];
+
+                        // if the new cell already has a value in the output, use that as the outletID
+                        z = output[(y, x)];
+                        if z != low_value {
+                            outlet_id = z;
+                            flag = true;
+                        }
+                    } else {
+                        flag = true;
+                    }
+                }
+
+                flag = false;
+                x = col;
+                y = row;
+                output[(y, x)] = outlet_id;
+                while !flag {
+                    // find its downslope neighbour
+                    dir = flow_dir[(y, x)];
+                    if dir >= 0 {
+                        // move x and y accordingly
+                        x += d_x[dir as usize];
+                        y += d_y[dir as usize];
+
+                        // if the new cell already has a value in the output, use that as the outletID
+                        if output[(y, x)] != low_value {
+                            flag = true;
+                        }
+                    } else {
+                        flag = true;
+                    }
+                    output[(y, x)] = outlet_id;
+                }
+            }
+        }
+        reporter.check_cancelled()?;
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Progress", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    Ok(output)
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 04/07/2017
-Last Modified: 15/11/2018
+Last Modified: 16/10/2026
 License: MIT
 
 NOTES: Add anisotropy option.
@@ -10,6 +10,7 @@ NOTES: Add anisotropy option.
 
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
+use super::raster_mask::feature_file_name;
 use crate::tools::*;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
@@ -46,6 +47,21 @@ use std::path;
 /// NoData values in the input cost surface image are ignored during processing and assigned NoData values
 /// in the outputs. The output cost accumulation raster is of the float data type and continuous data scale.
 ///
+/// The source and cost rasters must share the same grid. If `--align=auto` is specified, a source raster
+/// that differs in extent or resolution is instead resampled and cropped to the grid of the cost raster,
+/// using nearest-neighbour interpolation, with a warning.
+///
+/// Accumulation may be limited to a maximum cost (`--max_cost`), e.g. to map the catchment that lies
+/// within a travel budget of the sources. The search then stops once the budget is exhausted, which
+/// is much faster than accumulating over the whole surface, and cells with a greater accumulated cost,
+/// or that cannot be reached from the sources, are assigned NoData in both outputs.
+///
+/// The positive values of the source raster may also identify classes of sources, e.g. the ID of each
+/// of several facilities. If `--by_class` is specified, the accumulated cost and back-link rasters of
+/// each class are written, in addition to the outputs for all of the sources, to files named after
+/// the class value, e.g. `accum_3.tif` and `backlink_3.tif` for the sources with a value of 3. This
+/// avoids a separate run of the tool, and the reading of its inputs, for each class.
+///
 /// # See Also
 /// `CostAllocation`, `CostPathway`, `WeightedOverlay`
 pub struct CostDistance {
@@ -102,6 +118,33 @@ impl CostDistance {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Input Alignment".to_owned(),
+            flags: vec!["--align".to_owned()],
+            description: "Input alignment mode; 'auto' resamples the source raster to the grid of the cost raster.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["none".to_owned(), "auto".to_owned()]),
+            default_value: Some("none".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Accumulated Cost (optional)".to_owned(),
+            flags: vec!["--max_cost".to_owned()],
+            description: "Optional maximum accumulated cost; cells with a greater cost are assigned NoData.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output a surface for each source class?".to_owned(),
+            flags: vec!["--by_class".to_owned()],
+            description: "Output the accumulated cost and back-link rasters of each source value (class) too.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let e = format!("{}", env::current_exe().unwrap().display());
         let mut parent = env::current_exe().unwrap();
@@ -115,7 +158,8 @@ impl CostDistance {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --source=src.tif --cost=cost.tif --out_accum=accum.tif --out_backlink=backlink.tif", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --source=src.tif --cost=cost.tif --out_accum=accum.tif --out_backlink=backlink.tif
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --source=facilities.tif --cost=cost.tif --out_accum=accum.tif --out_backlink=backlink.tif --max_cost=5000.0 --by_class", short_exe, name).replace("*", &sep);
 
         CostDistance {
             name: name,
@@ -125,6 +169,14 @@ impl CostDistance {
             example_usage: usage,
         }
     }
+
+    /// Calculates the accumulated cost of travelling from the source cells (positive values) of an
+    /// in-memory raster over an in-memory cost surface, without reading or writing any files. Returns
+    /// the accumulated cost and back-link rasters. The source and cost rasters must share the same grid.
+    pub fn execute(source: &Raster, cost: &Raster) -> Result<(Raster, Raster), Error> {
+        check_same_grid(cost, source)?;
+        accumulate_cost(source, cost, None, f64::INFINITY, false, &ConsoleProgress)
+    }
 }
 
 impl WhiteboxTool for CostDistance {
@@ -160,53 +212,19 @@ impl WhiteboxTool for CostDistance {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut source_file = String::new();
-        let mut cost_file = String::new();
-        let mut accum_file = String::new();
-        let mut backlink_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-source" {
-                source_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-cost" {
-                cost_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_accum" {
-                accum_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_backlink" {
-                backlink_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut source_file = parsed.get_string("source").unwrap_or_default();
+        let mut cost_file = parsed.get_string("cost").unwrap_or_default();
+        let mut accum_file = parsed.get_string("out_accum").unwrap_or_default();
+        let mut backlink_file = parsed.get_string("out_backlink").unwrap_or_default();
+        let mut align = false;
+        let max_cost = parsed.get_f64("max_cost").unwrap_or(f64::INFINITY);
+        let by_class = parsed.get_bool("by_class");
+
+        if let Some(value) = parsed.get_string("align") {
+            align = value.is_empty() || value.to_lowercase().contains("auto");
         }
 
         if verbose {
@@ -220,485 +238,552 @@ impl WhiteboxTool for CostDistance {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
-
-        if !source_file.contains(&sep) && !source_file.contains("/") {
-            source_file = format!("{}{}", working_directory, source_file);
-        }
-        if !cost_file.contains(&sep) && !cost_file.contains("/") {
-            cost_file = format!("{}{}", working_directory, cost_file);
-        }
-        if !accum_file.contains(&sep) && !accum_file.contains("/") {
-            accum_file = format!("{}{}", working_directory, accum_file);
-        }
-        if !backlink_file.contains(&sep) && !backlink_file.contains("/") {
-            backlink_file = format!("{}{}", working_directory, backlink_file);
+        if max_cost.is_nan() || max_cost < 0.0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum accumulated cost (--max_cost) must not be negative.",
+            ));
         }
 
-        if verbose {
-            println!("Reading source data...")
-        };
-        let source = Raster::new(&source_file, "r")?;
+        source_file = resolve_input_path(&source_file, working_directory)?;
+        cost_file = resolve_input_path(&cost_file, working_directory)?;
+        accum_file = resolve_path(&accum_file, working_directory);
+        backlink_file = resolve_path(&backlink_file, working_directory);
 
         if verbose {
             println!("Reading cost data...")
         };
         let cost = Raster::new(&cost_file, "r")?;
 
+        if verbose {
+            println!("Reading source data...")
+        };
         // make sure the input files have the same size
-        if source.configs.rows != cost.configs.rows
-            || source.configs.columns != cost.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
+        let source = read_secondary_raster(&source_file, &cost, align, false, verbose)?;
 
         let start = Instant::now();
-        let rows = source.configs.rows as isize;
-        let columns = source.configs.columns as isize;
-        let num_cells = (rows * columns) as usize;
-        let nodata = cost.configs.nodata;
-
-        let mut output = Raster::initialize_using_file(&accum_file, &cost);
-        output.configs.data_type = DataType::F32;
-        let background_val = (i32::max_value() - 1) as f64;
-        output.reinitialize_values(background_val);
-
-        let mut backlink = Raster::initialize_using_file(&backlink_file, &cost);
+        let (mut output, mut backlink) =
+            accumulate_cost(&source, &cost, None, max_cost, verbose, reporter)?;
+        output.set_file_name(&accum_file);
+        backlink.set_file_name(&backlink_file);
 
-        let mut minheap = BinaryHeap::with_capacity(num_cells);
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let add_metadata = |raster: &mut Raster, elapsed_time: &str| {
+            raster.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            raster.add_metadata_entry(format!("Source raster file: {}", source_file));
+            raster.add_metadata_entry(format!("Cost raster: {}", cost_file));
+            if max_cost.is_finite() {
+                raster.add_metadata_entry(format!("Maximum accumulated cost: {}", max_cost));
+            }
+            raster.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+        };
+        add_metadata(&mut output, &elapsed_time);
+        add_metadata(&mut backlink, &elapsed_time);
 
-        let mut solved_cells = 0;
-        for row in 0..rows {
-            for col in 0..columns {
-                if source.get_value(row, col) > 0.0 && cost.get_value(row, col) != nodata {
-                    output.set_value(row, col, 0.0);
-                    backlink.set_value(row, col, 0.0);
-                    minheap.push(GridCell {
-                        row: row,
-                        column: col,
-                        priority: 0f64,
-                    });
-                    solved_cells += 1;
-                } else if cost.get_value(row, col) == nodata {
-                    output.set_value(row, col, nodata);
-                    solved_cells += 1;
+        if verbose {
+            println!("Saving data...")
+        };
+        for raster in [&mut output, &mut backlink] {
+            let _ = match raster.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+        drop(output);
+        drop(backlink);
+
+        if by_class {
+            // Each distinct positive source value is a class of sources.
+            let cost_nodata = cost.configs.nodata;
+            let mut classes = vec![];
+            for row in 0..source.configs.rows as isize {
+                reporter.check_cancelled()?;
+                for col in 0..source.configs.columns as isize {
+                    let z = source.get_value(row, col);
+                    if z > 0.0 && !is_nodata(cost.get_value(row, col), cost_nodata) {
+                        classes.push(z);
+                    }
                 }
             }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Initializing: {}%", progress);
-                    old_progress = progress;
+            classes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            classes.dedup();
+
+            for (i, &class) in classes.iter().enumerate() {
+                if verbose {
+                    println!("Source class {} ({} of {})...", class, i + 1, classes.len());
+                }
+                let class_start = Instant::now();
+                let (mut class_output, mut class_backlink) =
+                    accumulate_cost(&source, &cost, Some(class), max_cost, verbose, reporter)?;
+                class_output.set_file_name(&feature_file_name(&accum_file, class));
+                class_backlink.set_file_name(&feature_file_name(&backlink_file, class));
+                let class_elapsed_time = get_formatted_elapsed_time(class_start);
+                for raster in [&mut class_output, &mut class_backlink] {
+                    add_metadata(raster, &class_elapsed_time);
+                    raster.add_metadata_entry(format!("Source class: {}", class));
+                    let _ = match raster.write() {
+                        Ok(_) => {
+                            if verbose {
+                                println!("Output file written")
+                            }
+                        }
+                        Err(e) => return Err(e),
+                    };
                 }
             }
         }
 
-        let mut new_cost: f64;
-        let mut accum_val: f64;
-        let (mut cost1, mut cost2): (f64, f64);
-        let (mut row, mut col): (isize, isize);
-        let (mut row_n, mut col_n): (isize, isize);
-        let cell_size_x = source.configs.resolution_x;
-        let cell_size_y = source.configs.resolution_y;
-        let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
-        let dist = [
-            diag_cell_size,
-            cell_size_x,
-            diag_cell_size,
-            cell_size_y,
-            diag_cell_size,
-            cell_size_x,
-            diag_cell_size,
-            cell_size_y,
-        ];
-        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
-        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
-        let backlink_dir = [16.0, 32.0, 64.0, 128.0, 1.0, 2.0, 4.0, 8.0];
-        let mut solved: Array2D<i8> = Array2D::new(rows, columns, 0, -1)?;
-        while !minheap.is_empty() {
-            let cell = minheap.pop().expect("Error during pop operation.");
-            row = cell.row;
-            col = cell.column;
-            if solved.get_value(row, col) == 0 {
-                solved.set_value(row, col, 1);
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+// Accumulates the cost of travelling from the source cells over the cost surface, returning the
+// accumulated cost and back-link rasters. If `class` is given, only the source cells with that value
+// are used. Cells with an accumulated cost greater than `max_cost`, i.e. the cells that are not
+// solved before the search stops, are assigned NoData, unless it is infinite, in which case unreachable
+// cells keep the background value, as they always have.
+fn accumulate_cost(source: &Raster, cost: &Raster, class: Option<f64>, max_cost: f64, verbose: bool, reporter: &dyn ProgressReporter) -> Result<(Raster, Raster), Error> {
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+
+    let rows = source.configs.rows as isize;
+    let columns = source.configs.columns as isize;
+    let num_cells = (rows * columns) as usize;
+    let nodata = cost.configs.nodata;
+
+    let mut output = Raster::initialize_using_file("cost_accumulation.tif", cost);
+    output.configs.data_type = DataType::F32;
+    let background_val = (i32::max_value() - 1) as f64;
+    output.reinitialize_values(background_val);
+
+    let mut backlink = Raster::initialize_using_file("cost_backlink.tif", cost);
+
+    let mut minheap = BinaryHeap::with_capacity(num_cells);
+
+    let mut solved_cells = 0;
+    for row in 0..rows {
+        for col in 0..columns {
+            let z = source.get_value(row, col);
+            if z > 0.0 && class.map_or(true, |c| z == c) && !is_nodata(cost.get_value(row, col), nodata) {
+                output.set_value(row, col, 0.0);
+                backlink.set_value(row, col, 0.0);
+                minheap.push(GridCell {
+                    row: row,
+                    column: col,
+                    priority: 0f64,
+                });
                 solved_cells += 1;
-                accum_val = output.get_value(row, col);
-                cost1 = cost.get_value(row, col);
-                for n in 0..8 {
-                    col_n = col + dx[n];
-                    row_n = row + dy[n];
-                    if output.get_value(row_n, col_n) != nodata {
-                        cost2 = cost.get_value(row_n, col_n);
-                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
-                        if new_cost < output.get_value(row_n, col_n) {
-                            if solved.get_value(row_n, col_n) == 0 {
-                                output.set_value(row_n, col_n, new_cost);
-                                backlink.set_value(row_n, col_n, backlink_dir[n]);
-                                minheap.push(GridCell {
-                                    row: row_n,
-                                    column: col_n,
-                                    priority: new_cost,
-                                });
-                            }
+            } else if is_nodata(cost.get_value(row, col), nodata) {
+                output.set_value(row, col, nodata);
+                solved_cells += 1;
+            }
+        }
+        reporter.check_cancelled()?;
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Initializing", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    let mut new_cost: f64;
+    let mut accum_val: f64;
+    let (mut cost1, mut cost2): (f64, f64);
+    let (mut row, mut col): (isize, isize);
+    let (mut row_n, mut col_n): (isize, isize);
+    let cell_size_x = source.configs.resolution_x;
+    let cell_size_y = source.configs.resolution_y;
+    let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+    let dist = [
+        diag_cell_size,
+        cell_size_x,
+        diag_cell_size,
+        cell_size_y,
+        diag_cell_size,
+        cell_size_x,
+        diag_cell_size,
+        cell_size_y,
+    ];
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let backlink_dir = [16.0, 32.0, 64.0, 128.0, 1.0, 2.0, 4.0, 8.0];
+    let mut solved: Array2D<i8> = Array2D::new(rows, columns, 0, -1)?;
+    while !minheap.is_empty() {
+        let cell = minheap.pop().expect("Error during pop operation.");
+        if cell.priority > max_cost {
+            // every remaining cell is costlier still
+            break;
+        }
+        row = cell.row;
+        col = cell.column;
+        if solved.get_value(row, col) == 0 {
+            solved.set_value(row, col, 1);
+            solved_cells += 1;
+            accum_val = output.get_value(row, col);
+            cost1 = cost.get_value(row, col);
+            for n in 0..8 {
+                col_n = col + dx[n];
+                row_n = row + dy[n];
+                if !is_nodata(output.get_value(row_n, col_n), nodata) {
+                    cost2 = cost.get_value(row_n, col_n);
+                    new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                    if new_cost < output.get_value(row_n, col_n) {
+                        if solved.get_value(row_n, col_n) == 0 {
+                            output.set_value(row_n, col_n, new_cost);
+                            backlink.set_value(row_n, col_n, backlink_dir[n]);
+                            minheap.push(GridCell {
+                                row: row_n,
+                                column: col_n,
+                                priority: new_cost,
+                            });
                         }
                     }
                 }
-                if verbose {
-                    progress = (100.0_f64 * solved_cells as f64 / (num_cells - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Progress: {}%", progress);
-                        old_progress = progress;
-                    }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * solved_cells as f64 / (num_cells - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
                 }
             }
         }
+    }
 
-        /*
-        let mut new_cost: f64;
-        let mut accum_val: f64;
-        let (mut cost1, mut cost2): (f64, f64);
-        let (mut row_n, mut col_n): (isize, isize);
-        let cell_size_x = source.configs.resolution_x;
-        let cell_size_y = source.configs.resolution_y;
-        let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
-        let dist = [
-            diag_cell_size,
-            cell_size_x,
-            diag_cell_size,
-            cell_size_y,
-            diag_cell_size,
-            cell_size_x,
-            diag_cell_size,
-            cell_size_y,
-        ];
-        let dx = [1, 1, 0, -1, -1, -1, 0, 1];
-        let dy = [0, 1, 1, 1, 0, -1, -1, -1];
-        let backlink_dir = [32.0, 64.0, 128.0, 1.0, 2.0, 4.0, 8.0, 16.0];
-        let mut did_something = true;
-        let mut loop_num = 0;
-        while did_something {
-            // Row major scans
-
-            loop_num += 1;
-            did_something = false;
-            for row in 0..rows {
-                for col in 0..columns {
-                    accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
-                        cost1 = cost[(row, col)];
-                        for n in 0..8 {
-                            col_n = col + dx[n];
-                            row_n = row + dy[n];
-                            cost2 = cost[(row_n, col_n)];
-                            new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
-                            if new_cost < output[(row_n, col_n)] {
-                                output.set_value(row_n, col_n, new_cost);
-                                backlink.set_value(row_n, col_n, backlink_dir[n]);
-                                did_something = true;
-                            }
+    /*
+    let mut new_cost: f64;
+    let mut accum_val: f64;
+    let (mut cost1, mut cost2): (f64, f64);
+    let (mut row_n, mut col_n): (isize, isize);
+    let cell_size_x = source.configs.resolution_x;
+    let cell_size_y = source.configs.resolution_y;
+    let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+    let dist = [
+        diag_cell_size,
+        cell_size_x,
+        diag_cell_size,
+        cell_size_y,
+        diag_cell_size,
+        cell_size_x,
+        diag_cell_size,
+        cell_size_y,
+    ];
+    let dx = [1, 1, 0, -1, -1, -1, 0, 1];
+    let dy = [0, 1, 1, 1, 0, -1, -1, -1];
+    let backlink_dir = [32.0, 64.0, 128.0, 1.0, 2.0, 4.0, 8.0, 16.0];
+    let mut did_something = true;
+    let mut loop_num = 0;
+    while did_something {
+        // Row major scans
+
+        loop_num += 1;
+        did_something = false;
+        for row in 0..rows {
+            for col in 0..columns {
+                accum_val = output[(row, col)];
+                if accum_val < background_val && !is_nodata(accum_val, nodata) {
+                    cost1 = cost[(row, col)];
+                    for n in 0..8 {
+                        col_n = col + dx[n];
+                        row_n = row + dy[n];
+                        cost2 = cost[(row_n, col_n)];
+                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                        if new_cost < output[(row_n, col_n)] {
+                            output.set_value(row_n, col_n, new_cost);
+                            backlink.set_value(row_n, col_n, backlink_dir[n]);
+                            did_something = true;
                         }
                     }
                 }
-                if verbose {
-                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Loop {}: {}%", loop_num, progress);
-                        old_progress = progress;
-                    }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress(&format!("Loop {}", loop_num), progress);
+                    old_progress = progress;
                 }
             }
+        }
 
-            if !did_something {
-                break;
-            }
+        if !did_something {
+            break;
+        }
 
-            loop_num += 1;
-            did_something = false;
-            for row in (0..rows).rev() {
-                for col in (0..columns).rev() {
-                    accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
-                        cost1 = cost[(row, col)];
-                        for n in 0..8 {
-                            col_n = col + dx[n];
-                            row_n = row + dy[n];
-                            cost2 = cost[(row_n, col_n)];
-                            new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
-                            if new_cost < output[(row_n, col_n)] {
-                                output.set_value(row_n, col_n, new_cost);
-                                backlink.set_value(row_n, col_n, backlink_dir[n]);
-                                did_something = true;
-                            }
+        loop_num += 1;
+        did_something = false;
+        for row in (0..rows).rev() {
+            for col in (0..columns).rev() {
+                accum_val = output[(row, col)];
+                if accum_val < background_val && !is_nodata(accum_val, nodata) {
+                    cost1 = cost[(row, col)];
+                    for n in 0..8 {
+                        col_n = col + dx[n];
+                        row_n = row + dy[n];
+                        cost2 = cost[(row_n, col_n)];
+                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                        if new_cost < output[(row_n, col_n)] {
+                            output.set_value(row_n, col_n, new_cost);
+                            backlink.set_value(row_n, col_n, backlink_dir[n]);
+                            did_something = true;
                         }
                     }
                 }
-                if verbose {
-                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Loop {}: {}%", loop_num, progress);
-                        old_progress = progress;
-                    }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress(&format!("Loop {}", loop_num), progress);
+                    old_progress = progress;
                 }
             }
+        }
 
-            if !did_something {
-                break;
-            }
+        if !did_something {
+            break;
+        }
 
-            loop_num += 1;
-            did_something = false;
-            for row in 0..rows {
-                for col in (0..columns).rev() {
-                    accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
-                        cost1 = cost[(row, col)];
-                        for n in 0..8 {
-                            col_n = col + dx[n];
-                            row_n = row + dy[n];
-                            cost2 = cost[(row_n, col_n)];
-                            new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
-                            if new_cost < output[(row_n, col_n)] {
-                                output.set_value(row_n, col_n, new_cost);
-                                backlink.set_value(row_n, col_n, backlink_dir[n]);
-                                did_something = true;
-                            }
+        loop_num += 1;
+        did_something = false;
+        for row in 0..rows {
+            for col in (0..columns).rev() {
+                accum_val = output[(row, col)];
+                if accum_val < background_val && !is_nodata(accum_val, nodata) {
+                    cost1 = cost[(row, col)];
+                    for n in 0..8 {
+                        col_n = col + dx[n];
+                        row_n = row + dy[n];
+                        cost2 = cost[(row_n, col_n)];
+                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                        if new_cost < output[(row_n, col_n)] {
+                            output.set_value(row_n, col_n, new_cost);
+                            backlink.set_value(row_n, col_n, backlink_dir[n]);
+                            did_something = true;
                         }
                     }
                 }
-                if verbose {
-                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Loop {}: {}%", loop_num, progress);
-                        old_progress = progress;
-                    }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress(&format!("Loop {}", loop_num), progress);
+                    old_progress = progress;
                 }
             }
+        }
 
-            if !did_something {
-                break;
-            }
+        if !did_something {
+            break;
+        }
 
-            loop_num += 1;
-            did_something = false;
-            for row in (0..rows).rev() {
-                for col in 0..columns {
-                    accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
-                        cost1 = cost[(row, col)];
-                        for n in 0..8 {
-                            col_n = col + dx[n];
-                            row_n = row + dy[n];
-                            cost2 = cost[(row_n, col_n)];
-                            new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
-                            if new_cost < output[(row_n, col_n)] {
-                                output.set_value(row_n, col_n, new_cost);
-                                backlink.set_value(row_n, col_n, backlink_dir[n]);
-                                did_something = true;
-                            }
+        loop_num += 1;
+        did_something = false;
+        for row in (0..rows).rev() {
+            for col in 0..columns {
+                accum_val = output[(row, col)];
+                if accum_val < background_val && !is_nodata(accum_val, nodata) {
+                    cost1 = cost[(row, col)];
+                    for n in 0..8 {
+                        col_n = col + dx[n];
+                        row_n = row + dy[n];
+                        cost2 = cost[(row_n, col_n)];
+                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                        if new_cost < output[(row_n, col_n)] {
+                            output.set_value(row_n, col_n, new_cost);
+                            backlink.set_value(row_n, col_n, backlink_dir[n]);
+                            did_something = true;
                         }
                     }
                 }
-                if verbose {
-                    progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Loop {}: {}%", loop_num, progress);
-                        old_progress = progress;
-                    }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress(&format!("Loop {}", loop_num), progress);
+                    old_progress = progress;
                 }
             }
+        }
 
-            // Column major scans
+        // Column major scans
 
-            if !did_something {
-                break;
-            }
+        if !did_something {
+            break;
+        }
 
-            loop_num += 1;
-            did_something = false;
-            for col in 0..columns {
-                for row in 0..rows {
-                    accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
-                        cost1 = cost[(row, col)];
-                        for n in 0..8 {
-                            col_n = col + dx[n];
-                            row_n = row + dy[n];
-                            cost2 = cost[(row_n, col_n)];
-                            new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
-                            if new_cost < output[(row_n, col_n)] {
-                                output.set_value(row_n, col_n, new_cost);
-                                backlink.set_value(row_n, col_n, backlink_dir[n]);
-                                did_something = true;
-                            }
+        loop_num += 1;
+        did_something = false;
+        for col in 0..columns {
+            for row in 0..rows {
+                accum_val = output[(row, col)];
+                if accum_val < background_val && !is_nodata(accum_val, nodata) {
+                    cost1 = cost[(row, col)];
+                    for n in 0..8 {
+                        col_n = col + dx[n];
+                        row_n = row + dy[n];
+                        cost2 = cost[(row_n, col_n)];
+                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                        if new_cost < output[(row_n, col_n)] {
+                            output.set_value(row_n, col_n, new_cost);
+                            backlink.set_value(row_n, col_n, backlink_dir[n]);
+                            did_something = true;
                         }
                     }
                 }
-                if verbose {
-                    progress = (100.0_f64 * col as f64 / (columns - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Loop {}: {}%", loop_num, progress);
-                        old_progress = progress;
-                    }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * col as f64 / (columns - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress(&format!("Loop {}", loop_num), progress);
+                    old_progress = progress;
                 }
             }
+        }
 
-            if !did_something {
-                break;
-            }
+        if !did_something {
+            break;
+        }
 
-            loop_num += 1;
-            did_something = false;
-            for col in (0..columns).rev() {
-                for row in (0..rows).rev() {
-                    accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
-                        cost1 = cost[(row, col)];
-                        for n in 0..8 {
-                            col_n = col + dx[n];
-                            row_n = row + dy[n];
-                            cost2 = cost[(row_n, col_n)];
-                            new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
-                            if new_cost < output[(row_n, col_n)] {
-                                output.set_value(row_n, col_n, new_cost);
-                                backlink.set_value(row_n, col_n, backlink_dir[n]);
-                                did_something = true;
-                            }
+        loop_num += 1;
+        did_something = false;
+        for col in (0..columns).rev() {
+            for row in (0..rows).rev() {
+                accum_val = output[(row, col)];
+                if accum_val < background_val && !is_nodata(accum_val, nodata) {
+                    cost1 = cost[(row, col)];
+                    for n in 0..8 {
+                        col_n = col + dx[n];
+                        row_n = row + dy[n];
+                        cost2 = cost[(row_n, col_n)];
+                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                        if new_cost < output[(row_n, col_n)] {
+                            output.set_value(row_n, col_n, new_cost);
+                            backlink.set_value(row_n, col_n, backlink_dir[n]);
+                            did_something = true;
                         }
                     }
                 }
-                if verbose {
-                    progress = (100.0_f64 * col as f64 / (columns - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Loop {}: {}%", loop_num, progress);
-                        old_progress = progress;
-                    }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * col as f64 / (columns - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress(&format!("Loop {}", loop_num), progress);
+                    old_progress = progress;
                 }
             }
+        }
 
-            if !did_something {
-                break;
-            }
+        if !did_something {
+            break;
+        }
 
-            loop_num += 1;
-            did_something = false;
-            for col in (0..columns).rev() {
-                for row in 0..rows {
-                    accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
-                        cost1 = cost[(row, col)];
-                        for n in 0..8 {
-                            col_n = col + dx[n];
-                            row_n = row + dy[n];
-                            cost2 = cost[(row_n, col_n)];
-                            new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
-                            if new_cost < output[(row_n, col_n)] {
-                                output.set_value(row_n, col_n, new_cost);
-                                backlink.set_value(row_n, col_n, backlink_dir[n]);
-                                did_something = true;
-                            }
+        loop_num += 1;
+        did_something = false;
+        for col in (0..columns).rev() {
+            for row in 0..rows {
+                accum_val = output[(row, col)];
+                if accum_val < background_val && !is_nodata(accum_val, nodata) {
+                    cost1 = cost[(row, col)];
+                    for n in 0..8 {
+                        col_n = col + dx[n];
+                        row_n = row + dy[n];
+                        cost2 = cost[(row_n, col_n)];
+                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                        if new_cost < output[(row_n, col_n)] {
+                            output.set_value(row_n, col_n, new_cost);
+                            backlink.set_value(row_n, col_n, backlink_dir[n]);
+                            did_something = true;
                         }
                     }
                 }
-                if verbose {
-                    progress = (100.0_f64 * col as f64 / (columns - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Loop {}: {}%", loop_num, progress);
-                        old_progress = progress;
-                    }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * col as f64 / (columns - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress(&format!("Loop {}", loop_num), progress);
+                    old_progress = progress;
                 }
             }
+        }
 
-            if !did_something {
-                break;
-            }
+        if !did_something {
+            break;
+        }
 
-            loop_num += 1;
-            did_something = false;
-            for col in 0..columns {
-                for row in (0..rows).rev() {
-                    accum_val = output[(row, col)];
-                    if accum_val < background_val && accum_val != nodata {
-                        cost1 = cost[(row, col)];
-                        for n in 0..8 {
-                            col_n = col + dx[n];
-                            row_n = row + dy[n];
-                            cost2 = cost[(row_n, col_n)];
-                            new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
-                            if new_cost < output[(row_n, col_n)] {
-                                output.set_value(row_n, col_n, new_cost);
-                                backlink.set_value(row_n, col_n, backlink_dir[n]);
-                                did_something = true;
-                            }
+        loop_num += 1;
+        did_something = false;
+        for col in 0..columns {
+            for row in (0..rows).rev() {
+                accum_val = output[(row, col)];
+                if accum_val < background_val && !is_nodata(accum_val, nodata) {
+                    cost1 = cost[(row, col)];
+                    for n in 0..8 {
+                        col_n = col + dx[n];
+                        row_n = row + dy[n];
+                        cost2 = cost[(row_n, col_n)];
+                        new_cost = accum_val + (cost1 + cost2) / 2.0 * dist[n];
+                        if new_cost < output[(row_n, col_n)] {
+                            output.set_value(row_n, col_n, new_cost);
+                            backlink.set_value(row_n, col_n, backlink_dir[n]);
+                            did_something = true;
                         }
                     }
                 }
-                if verbose {
-                    progress = (100.0_f64 * col as f64 / (columns - 1) as f64) as usize;
-                    if progress != old_progress {
-                        println!("Loop {}: {}%", loop_num, progress);
-                        old_progress = progress;
-                    }
-                }
             }
-        }
-        */
-
-        let elapsed_time = get_formatted_elapsed_time(start);
-        output.configs.palette = "spectrum.plt".to_string();
-        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
-        output.add_metadata_entry(format!(
-            "Created by whitebox_tools\' {} tool",
-            self.get_tool_name()
-        ));
-        output.add_metadata_entry(format!("Source raster file: {}", source_file));
-        output.add_metadata_entry(format!("Cost raster: {}", cost_file));
-        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
-
-        if verbose {
-            println!("Saving data...")
-        };
-        let _ = match output.write() {
-            Ok(_) => {
-                if verbose {
-                    println!("Output file written")
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * col as f64 / (columns - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress(&format!("Loop {}", loop_num), progress);
+                    old_progress = progress;
                 }
             }
-            Err(e) => return Err(e),
-        };
+        }
+    }
+    */
 
-        backlink.configs.palette = "qual.plt".to_string();
-        backlink.configs.photometric_interp = PhotometricInterpretation::Categorical;
-        backlink.add_metadata_entry(format!(
-            "Created by whitebox_tools\' {} tool",
-            self.get_tool_name()
-        ));
-        backlink.add_metadata_entry(format!("Source raster file: {}", source_file));
-        backlink.add_metadata_entry(format!("Cost raster: {}", cost_file));
-        backlink.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
-        let _ = match backlink.write() {
-            Ok(_) => {
-                if verbose {
-                    println!("Output file written")
+    if max_cost.is_finite() {
+        // Test the cells that were not solved, rather than the accumulated costs, so that unreachable
+        // cells, which hold the background value, are also assigned NoData when max_cost exceeds it.
+        for row in 0..rows {
+            for col in 0..columns {
+                if solved.get_value(row, col) == 0 && !is_nodata(output.get_value(row, col), nodata) {
+                    output.set_value(row, col, nodata);
+                    backlink.set_value(row, col, nodata);
                 }
             }
-            Err(e) => return Err(e),
-        };
-
-        if verbose {
-            println!(
-                "{}",
-                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
-            );
         }
-
-        Ok(())
     }
+
+    output.configs.palette = "spectrum.plt".to_string();
+    output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+    backlink.configs.palette = "qual.plt".to_string();
+    backlink.configs.photometric_interp = PhotometricInterpretation::Categorical;
+
+    Ok((output, backlink))
 }
 
 #[derive(PartialEq, Debug)]
@@ -729,3 +814,78 @@ impl Ord for GridCell {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::accumulate_cost;
+    use crate::tools::ConsoleProgress;
+    use whitebox_raster::{Raster, RasterConfigs};
+
+    const NODATA: f64 = -32768.0;
+
+    // A single row of cells with a unit cost and resolution, so that accumulated costs are distances.
+    fn raster(file_name: &str, values: &[f64]) -> Raster {
+        let configs = RasterConfigs {
+            rows: 1,
+            columns: values.len(),
+            north: 1.0,
+            south: 0.0,
+            east: values.len() as f64,
+            west: 0.0,
+            resolution_x: 1.0,
+            resolution_y: 1.0,
+            nodata: NODATA,
+            ..Default::default()
+        };
+        let mut r = Raster::initialize_using_config(file_name, &configs);
+        for (col, value) in values.iter().enumerate() {
+            r.set_value(0, col as isize, *value);
+        }
+        r
+    }
+
+    fn row_values(r: &Raster) -> Vec<f64> {
+        (0..r.configs.columns as isize).map(|col| r.get_value(0, col)).collect()
+    }
+
+    #[test]
+    fn test_accumulate_cost_max_cost() {
+        let source = raster("source.tif", &[1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        let cost = raster("cost.tif", &[1.0; 6]);
+        let (accum, backlink) =
+            accumulate_cost(&source, &cost, None, f64::INFINITY, false, &ConsoleProgress).unwrap();
+        assert_eq!(row_values(&accum), vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        let (accum, backlink_limited) =
+            accumulate_cost(&source, &cost, None, 2.5, false, &ConsoleProgress).unwrap();
+        assert_eq!(row_values(&accum), vec![0.0, 1.0, 2.0, NODATA, NODATA, NODATA]);
+        let backlink = row_values(&backlink);
+        assert_eq!(row_values(&backlink_limited), vec![backlink[0], backlink[1], backlink[2], NODATA, NODATA, NODATA]);
+    }
+
+    #[test]
+    fn test_accumulate_cost_by_class() {
+        let source = raster("source.tif", &[1.0, 0.0, 0.0, 0.0, 0.0, 2.0]);
+        let cost = raster("cost.tif", &[1.0; 6]);
+        let (accum, _) = accumulate_cost(&source, &cost, None, f64::INFINITY, false, &ConsoleProgress).unwrap();
+        assert_eq!(row_values(&accum), vec![0.0, 1.0, 2.0, 2.0, 1.0, 0.0]);
+        // each class's surface ignores the sources of the other classes
+        let (accum, _) = accumulate_cost(&source, &cost, Some(1.0), f64::INFINITY, false, &ConsoleProgress).unwrap();
+        assert_eq!(row_values(&accum), vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        let (accum, _) = accumulate_cost(&source, &cost, Some(2.0), 3.5, false, &ConsoleProgress).unwrap();
+        assert_eq!(row_values(&accum), vec![NODATA, NODATA, 3.0, 2.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_accumulate_cost_max_cost_above_background() {
+        // the last cell cannot be reached across the NoData cell
+        let source = raster("source.tif", &[1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        let cost = raster("cost.tif", &[1.0, 1.0, 1.0, 1.0, NODATA, 1.0]);
+        let background = (i32::MAX - 1) as f64;
+        let (accum, _) = accumulate_cost(&source, &cost, None, f64::INFINITY, false, &ConsoleProgress).unwrap();
+        assert_eq!(row_values(&accum), vec![0.0, 1.0, 2.0, 3.0, NODATA, background]);
+        let (accum, backlink) = accumulate_cost(&source, &cost, None, 1.0e10, false, &ConsoleProgress).unwrap();
+        assert_eq!(row_values(&accum), vec![0.0, 1.0, 2.0, 3.0, NODATA, NODATA]);
+        assert_eq!(backlink.get_value(0, 5), NODATA);
+    }
+}
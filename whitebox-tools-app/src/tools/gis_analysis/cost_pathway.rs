@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 04/07/2017
-Last Modified: 18/10/2019
+Last Modified: 16/10/2026
 License: MIT
 */
 
@@ -10,7 +10,7 @@ use whitebox_raster::*;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to map the least-cost pathway connecting each destination grid cell
@@ -24,6 +24,10 @@ use std::path;
 ///
 /// NoData values in the input *back-link* image are assigned NoData values in the output image.
 ///
+/// The destination and back-link rasters must share the same grid. If `--align=auto` is specified, a
+/// destination raster that differs in extent or resolution is instead resampled and cropped to the grid
+/// of the back-link raster, using nearest-neighbour interpolation, with a warning.
+///
 /// # See Also
 /// `CostDistance`, `CostAllocation`
 pub struct CostPathway {
@@ -74,7 +78,7 @@ impl CostPathway {
 
         parameters.push(ToolParameter {
             name: "Treat zero values as background?".to_owned(),
-            flags: vec!["--zero_background".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--zero_background".to_owned()],
             description: "Flag indicating whether zero values should be treated as a background."
                 .to_owned(),
             parameter_type: ParameterType::Boolean,
@@ -82,6 +86,15 @@ impl CostPathway {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Input Alignment".to_owned(),
+            flags: vec!["--align".to_owned()],
+            description: "Input alignment mode; 'auto' resamples the destination raster to the grid of the back-link raster.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["none".to_owned(), "auto".to_owned()]),
+            default_value: Some("none".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let e = format!("{}", env::current_exe().unwrap().display());
         let mut parent = env::current_exe().unwrap();
@@ -105,6 +118,19 @@ impl CostPathway {
             example_usage: usage,
         }
     }
+
+    /// Traces the least-cost pathways from the destination cells of an in-memory raster down an
+    /// in-memory back-link raster, e.g. from `CostDistance::execute`, without reading or writing any
+    /// files. Cells that are not on a pathway are zero if `zero_background` is true, and NoData
+    /// otherwise. The destination and back-link rasters must share the same grid.
+    pub fn execute(
+        destination: &Raster,
+        backlink: &Raster,
+        zero_background: bool,
+    ) -> Result<Raster, Error> {
+        check_same_grid(destination, backlink)?;
+        trace_pathways(destination, backlink, zero_background, false, &ConsoleProgress)
+    }
 }
 
 impl WhiteboxTool for CostPathway {
@@ -140,54 +166,17 @@ impl WhiteboxTool for CostPathway {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut destination_file = String::new();
-        let mut backlink_file = String::new();
-        let mut output_file = String::new();
-        let mut background_val = f64::NEG_INFINITY;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-destination" || vec[0].to_lowercase() == "--destination" {
-                if keyval {
-                    destination_file = vec[1].to_string();
-                } else {
-                    destination_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-backlink" || vec[0].to_lowercase() == "--backlink"
-            {
-                if keyval {
-                    backlink_file = vec[1].to_string();
-                } else {
-                    backlink_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-zero_background"
-                || vec[0].to_lowercase() == "--zero_background"
-                || vec[0].to_lowercase() == "--esri_style"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    background_val = 0f64;
-                }
-            }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut destination_file = parsed.get_string("destination").unwrap_or_default();
+        let mut backlink_file = parsed.get_string("backlink").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let zero_background = parsed.get_bool("zero_background");
+        let mut align = false;
+
+        if let Some(value) = parsed.get_string("align") {
+            align = value.is_empty() || value.to_lowercase().contains("auto");
         }
 
         if verbose {
@@ -201,108 +190,26 @@ impl WhiteboxTool for CostPathway {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
-
-        if !destination_file.contains(&sep) && !destination_file.contains("/") {
-            destination_file = format!("{}{}", working_directory, destination_file);
-        }
-        if !backlink_file.contains(&sep) && !backlink_file.contains("/") {
-            backlink_file = format!("{}{}", working_directory, backlink_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
-
-        if verbose {
-            println!("Reading destination data...")
-        };
-        let destination = Raster::new(&destination_file, "r")?;
+        destination_file = resolve_input_path(&destination_file, working_directory)?;
+        backlink_file = resolve_input_path(&backlink_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading backlink data...")
         };
         let backlink = Raster::new(&backlink_file, "r")?;
 
+        if verbose {
+            println!("Reading destination data...")
+        };
         // make sure the input files have the same size
-        if destination.configs.rows != backlink.configs.rows
-            || destination.configs.columns != backlink.configs.columns
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
-        }
+        let destination = read_secondary_raster(&destination_file, &backlink, align, false, verbose)?;
 
         let start = Instant::now();
-        let rows = destination.configs.rows as isize;
-        let columns = destination.configs.columns as isize;
-        let nodata = backlink.configs.nodata;
-        if background_val == f64::NEG_INFINITY {
-            background_val = nodata;
-        }
-
-        let mut output = Raster::initialize_using_file(&output_file, &destination);
-        output.configs.data_type = DataType::F32;
-        output.reinitialize_values(background_val);
-
-        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
-        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
-        let mut pntr_matches: [usize; 129] = [0usize; 129];
-        // This maps Whitebox-style D8 pointer values
-        // onto the cell offsets in dx and dy.
-        pntr_matches[1] = 0usize;
-        pntr_matches[2] = 1usize;
-        pntr_matches[4] = 2usize;
-        pntr_matches[8] = 3usize;
-        pntr_matches[16] = 4usize;
-        pntr_matches[32] = 5usize;
-        pntr_matches[64] = 6usize;
-        pntr_matches[128] = 7usize;
-        let (mut x, mut y): (isize, isize);
-        let mut flag: bool;
-        let mut dir: f64;
-        for row in 0..rows {
-            for col in 0..columns {
-                if destination[(row, col)] > 0.0 && backlink[(row, col)] != nodata {
-                    flag = false;
-                    x = col;
-                    y = row;
-                    while !flag {
-                        if output[(y, x)] == background_val {
-                            output[(y, x)] = 1.0;
-                        } else {
-                            output.increment(y, x, 1.0);
-                        }
-                        // find its downslope neighbour
-                        dir = backlink[(y, x)];
-                        if dir != nodata && dir > 0.0 {
-                            // move x and y accordingly
-                            x += dx[pntr_matches[dir as usize]];
-                            y += dy[pntr_matches[dir as usize]];
-                        } else {
-                            flag = true;
-                        }
-                    }
-                } else if backlink[(row, col)] == nodata {
-                    output[(row, col)] = nodata;
-                }
-            }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
-                }
-            }
-        }
+        let mut output = trace_pathways(&destination, &backlink, zero_background, verbose, reporter)?;
+        output.set_file_name(&output_file);
 
         let elapsed_time = get_formatted_elapsed_time(start);
-        output.configs.palette = "spectrum.plt".to_string();
-        output.configs.data_type = DataType::F32;
-        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
         output.add_metadata_entry(format!(
             "Created by whitebox_tools\' {} tool",
             self.get_tool_name()
@@ -333,3 +240,82 @@ impl WhiteboxTool for CostPathway {
         Ok(())
     }
 }
+
+// Traces the least-cost pathways from each destination cell, counting the pathways through each cell.
+fn trace_pathways(
+    destination: &Raster,
+    backlink: &Raster,
+    zero_background: bool,
+    verbose: bool,
+    reporter: &dyn ProgressReporter,
+) -> Result<Raster, Error> {
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+
+    let rows = destination.configs.rows as isize;
+    let columns = destination.configs.columns as isize;
+    let nodata = backlink.configs.nodata;
+
+    let mut output = Raster::initialize_using_file("cost_pathway.tif", destination);
+    let out_nodata = output.configs.nodata;
+    let background_val = if zero_background { 0f64 } else { out_nodata };
+    output.configs.data_type = DataType::F32;
+    output.reinitialize_values(background_val);
+
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let mut pntr_matches: [usize; 129] = [0usize; 129];
+    // This maps Whitebox-style D8 pointer values
+    // onto the cell offsets in dx and dy.
+    pntr_matches[1] = 0usize;
+    pntr_matches[2] = 1usize;
+    pntr_matches[4] = 2usize;
+    pntr_matches[8] = 3usize;
+    pntr_matches[16] = 4usize;
+    pntr_matches[32] = 5usize;
+    pntr_matches[64] = 6usize;
+    pntr_matches[128] = 7usize;
+    let (mut x, mut y): (isize, isize);
+    let mut flag: bool;
+    let mut dir: f64;
+    for row in 0..rows {
+        for col in 0..columns {
+            if destination[(row, col)] > 0.0 && !is_nodata(backlink[(row, col)], nodata) {
+                flag = false;
+                x = col;
+                y = row;
+                while !flag {
+                    if output[(y, x)] == background_val {
+                        output[(y, x)] = 1.0;
+                    } else {
+                        output.increment(y, x, 1.0);
+                    }
+                    // find its downslope neighbour
+                    dir = backlink[(y, x)];
+                    if !is_nodata(dir, nodata) && dir > 0.0 {
+                        // move x and y accordingly
+                        x += dx[pntr_matches[dir as usize]];
+                        y += dy[pntr_matches[dir as usize]];
+                    } else {
+                        flag = true;
+                    }
+                }
+            } else if is_nodata(backlink[(row, col)], nodata) {
+                output[(row, col)] = out_nodata;
+            }
+        }
+        reporter.check_cancelled()?;
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Progress", progress);
+                old_progress = progress;
+            }
+        }
+    }
+    output.configs.palette = "spectrum.plt".to_string();
+    output.configs.data_type = DataType::F32;
+    output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+
+    Ok(output)
+}
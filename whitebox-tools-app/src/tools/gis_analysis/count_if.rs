@@ -124,53 +124,12 @@ impl WhiteboxTool for CountIf {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files = String::new();
-        let mut output_file = String::new();
-        let mut comparison_value = f64::MIN;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-value" {
-                comparison_value = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let comparison_value = parsed.get_f64("value").unwrap_or(f64::MIN);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -183,14 +142,10 @@ impl WhiteboxTool for CountIf {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut cmd = input_files.split(";");
         let mut vec = cmd.collect::<Vec<&str>>();
@@ -229,9 +184,7 @@ impl WhiteboxTool for CountIf {
                 };
 
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 let input = Raster::new(&input_file, "r")?;
                 in_nodata = input.configs.nodata;
                 if !read_first_file {
@@ -246,25 +199,25 @@ impl WhiteboxTool for CountIf {
                 // check to ensure that all inputs have the same rows and columns
                 if input.configs.rows as isize != rows || input.configs.columns as isize != columns
                 {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                "The input files must have the same number of rows and columns and spatial extent."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
                 }
 
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z == comparison_value && z != in_nodata {
-                            if output.get_value(row, col) != out_nodata {
+                        if z == comparison_value && !is_nodata(z, in_nodata) {
+                            if !is_nodata(output.get_value(row, col), out_nodata) {
                                 output.increment(row, col, 1f64);
                             } else {
                                 output.set_value(row, col, 1f64);
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress (loop {} of {}): {}%", i, num_files, progress);
+                            reporter.progress(&format!("Progress (loop {} of {})", i, num_files), progress);
                             old_progress = progress;
                         }
                     }
@@ -8,13 +8,12 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to create a new raster with values that are determined by the equation of a simple plane. The user
 /// must specify the name of a base raster (`--base`) from which the output raster coordinate and dimensional information
@@ -147,78 +146,17 @@ impl WhiteboxTool for CreatePlane {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut base_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut slope = 15.0;
-        let mut aspect = 90.0;
-        let mut constant_val = 0.0;
+        let mut aspect = parsed.get_f64("aspect").unwrap_or(90.0);
+        let constant_val = parsed.get_f64("constant").unwrap_or(0.0);
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-base" {
-                if keyval {
-                    base_file = vec[1].to_string();
-                } else {
-                    base_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-slope" {
-                if keyval {
-                    slope = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    slope = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-aspect" {
-                if keyval {
-                    aspect = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    aspect = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-constant" {
-                if keyval {
-                    constant_val = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    constant_val = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
+        if let Some(value) = parsed.get_silent_f64("slope")? {
+            slope = value;
         }
 
         if verbose {
@@ -232,14 +170,8 @@ impl WhiteboxTool for CreatePlane {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !base_file.contains(&sep) && !base_file.contains("/") {
-            base_file = format!("{}{}", working_directory, base_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        base_file = resolve_input_path(&base_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let base = Raster::new(&base_file, "r")?;
 
@@ -255,6 +187,7 @@ impl WhiteboxTool for CreatePlane {
         if aspect > 360.0 {
             let mut flag = false;
             while !flag {
+                reporter.check_cancelled()?;
                 aspect -= 360.0;
                 if aspect <= 360.0 {
                     flag = true;
@@ -286,16 +219,12 @@ impl WhiteboxTool for CreatePlane {
             output.configs.data_type = DataType::F32;
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut x, mut y): (f64, f64);
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
@@ -305,7 +234,9 @@ impl WhiteboxTool for CreatePlane {
                         data[col as usize] =
                             slope * aspect.sin() * x + slope * aspect.cos() * y + constant_val;
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -313,10 +244,11 @@ impl WhiteboxTool for CreatePlane {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -168,50 +168,15 @@ impl WhiteboxTool for Difference {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut overlay_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut overlay_file = parsed.get_string("overlay").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val.contains("-input") {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-overlay" {
-                overlay_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -228,15 +193,9 @@ impl WhiteboxTool for Difference {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !overlay_file.contains(&sep) && !overlay_file.contains("/") {
-            overlay_file = format!("{}{}", working_directory, overlay_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        overlay_file = resolve_input_path(&overlay_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -304,11 +263,13 @@ impl WhiteboxTool for Difference {
                 let mut tree = KdTree::with_capacity(dimensions, capacity_per_node);
                 let mut p: Point2D;
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     p = record.points[0];
                     tree.add([p.x, p.y], 1).unwrap();
                 }
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     p = record.points[0];
                     tree.add([p.x, p.y], 2).unwrap();
@@ -341,10 +302,11 @@ impl WhiteboxTool for Difference {
                         let atts = input.attributes.get_record(record_num);
                         output.attributes.add_record(atts, false);
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * record_num as f64 / num_total_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -358,6 +320,7 @@ impl WhiteboxTool for Difference {
                 let mut p: Point2D;
                 let mut total_points = 0;
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for p in &record.points {
                         tree.add([p.x, p.y], 1).unwrap();
@@ -365,6 +328,7 @@ impl WhiteboxTool for Difference {
                     }
                 }
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     for p in &record.points {
                         tree.add([p.x, p.y], 2).unwrap();
@@ -390,10 +354,11 @@ impl WhiteboxTool for Difference {
                                 break;
                             }
                         }
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * i as f64 / num_total_points) as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -413,6 +378,7 @@ impl WhiteboxTool for Difference {
                     match input.header.shape_type.dimension() {
                         ShapeTypeDimension::XY => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i] {
@@ -423,6 +389,7 @@ impl WhiteboxTool for Difference {
                         }
                         ShapeTypeDimension::Measure => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i] {
@@ -436,6 +403,7 @@ impl WhiteboxTool for Difference {
                         }
                         ShapeTypeDimension::Z => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i] {
@@ -464,6 +432,7 @@ impl WhiteboxTool for Difference {
                 let mut last_point_in_part: usize;
                 let mut polylines: Vec<Polyline> = vec![];
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -487,6 +456,7 @@ impl WhiteboxTool for Difference {
                 }
 
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -547,10 +517,11 @@ impl WhiteboxTool for Difference {
 
                     num_neighbours.push(line_num_neighbours);
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -580,10 +551,11 @@ impl WhiteboxTool for Difference {
                     }
                     features_polylines.push(pl.clone());
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -592,6 +564,7 @@ impl WhiteboxTool for Difference {
                 // Find duplicate polylines and remove them
                 let mut duplicate = vec![false; features_polylines.len()];
                 for i in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     if !duplicate[i] {
                         for j in (i + 1)..features_polylines.len() {
                             if features_polylines[i] == features_polylines[j] {
@@ -606,6 +579,7 @@ impl WhiteboxTool for Difference {
                     }
                 }
                 for i in (0..features_polylines.len()).rev() {
+                    reporter.check_cancelled()?;
                     if duplicate[i] {
                         features_polylines.remove(i);
                     }
@@ -613,6 +587,7 @@ impl WhiteboxTool for Difference {
 
                 // let mut fid = 1i32;
                 for i in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     if features_polylines[i].source_file == 1 {
                         let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
                         sfg.add_part(&features_polylines[i].vertices);
@@ -635,6 +610,7 @@ impl WhiteboxTool for Difference {
 
                 // Read in the features
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     let mut mpl = MultiPolyline::new(record_num);
                     let mut holes = vec![false; record.num_parts as usize];
@@ -663,6 +639,7 @@ impl WhiteboxTool for Difference {
                 }
 
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     let mut mpl = MultiPolyline::new(record_num);
                     let mut holes = vec![false; record.num_parts as usize];
@@ -1694,12 +1671,13 @@ impl WhiteboxTool for Difference {
                             output.attributes.add_record(atts, false);
                         }
 
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (record_num + 1) as f64
                                 / multipolylines.len() as f64)
                                 as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -0,0 +1,397 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::structures::Array2D;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool transfers the values of a coarse-resolution raster (`--input`) onto a finer grid, i.e. it is
+/// the inverse of `AggregateRaster`. The output grid is defined either by an integer disaggregation factor
+/// (`--factor`), in which case each input cell is split into *factor* x *factor* output cells, or by an
+/// existing fine-resolution raster (`--base`), in which case each output cell is related to the input cell
+/// that contains its centre.
+///
+/// The `--method` parameter determines how the coarse value is distributed among the fine cells. The
+/// `replicate` method copies the coarse value into each fine cell and is appropriate for intensive
+/// quantities, such as densities, rates, or elevations. The `divide` method splits the coarse value among
+/// its fine cells such that their sum equals the original value, and is appropriate for extensive quantities,
+/// such as population counts or crop production totals. By default, extensive values are divided equally.
+/// When a raster of weights (`--weights`) is supplied, which must share the output grid, each fine cell
+/// receives a share of the coarse value proportional to its weight, i.e. a dasymetric mapping. For example,
+/// census counts may be distributed using a land-cover-derived weight raster that assigns zero weight to
+/// uninhabited classes. Fine cells with *NoData* or negative weights are treated as having zero weight, and
+/// coarse cells that contain no positive weight are divided equally among their fine cells. With the
+/// `replicate` method, weights are used only to mask the output, i.e. cells of zero weight receive a value of
+/// zero.
+///
+/// # See Also
+/// `AggregateRaster`, `BlockStatistics`, `Resample`
+pub struct DisaggregateRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl DisaggregateRaster {
+    pub fn new() -> DisaggregateRaster {
+        // public constructor
+        let name = "DisaggregateRaster".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Splits the cells of a coarse raster onto a finer grid, with optional dasymetric weights.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input coarse-resolution raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Disaggregation Factor (cells)".to_owned(),
+            flags: vec!["--factor".to_owned()],
+            description: "Number of output cells per input cell, in each direction; ignored if a base or weights raster is specified.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optional raster defining the output grid.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Weights File (optional)".to_owned(),
+            flags: vec!["--weights".to_owned()],
+            description: "Optional dasymetric weights raster, defining the output grid.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Disaggregation method; 'replicate' for intensive and 'divide' for extensive quantities.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "replicate".to_owned(),
+                "divide".to_owned(),
+            ]),
+            default_value: Some("replicate".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=census.tif -o=output.tif --weights=landcover_weights.tif --method=divide",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        DisaggregateRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for DisaggregateRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let factor = parsed.get_i64("factor").map(|v| v as isize).unwrap_or(2isize);
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+        let mut weights_file = parsed.get_string("weights").unwrap_or_default();
+        let mut method = String::from("replicate");
+
+        if let Some(value) = parsed.get_string("method") {
+            method = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let divide = if method.contains("div") {
+            true
+        } else if method.contains("rep") {
+            false
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --method parameter must be either 'replicate' or 'divide'.",
+            ));
+        };
+
+        for file in [&mut input_file, &mut output_file, &mut base_file, &mut weights_file] {
+            *file = resolve_path(file, working_directory);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let weights = if !weights_file.is_empty() {
+            Some(Raster::new(&weights_file, "r")?)
+        } else {
+            None
+        };
+        let base = if !base_file.is_empty() {
+            let base = Raster::new(&base_file, "r")?;
+            if let Some(w) = &weights {
+                if !w.is_aligned_with(&base.configs) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The weights raster must share the grid of the base raster.",
+                    ));
+                }
+            }
+            Some(base)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let nodata = input.configs.nodata;
+
+        // Define the fine output grid.
+        let mut configs = input.configs.clone();
+        // the grid differs from the input's, so its GeoTIFF georeferencing tags don't apply
+        configs.model_tiepoint = vec![];
+        configs.model_pixel_scale = [0f64; 3];
+        configs.model_transformation = [0f64; 16];
+        if let Some(grid) = base.as_ref().or(weights.as_ref()) {
+            configs.rows = grid.configs.rows;
+            configs.columns = grid.configs.columns;
+            configs.north = grid.configs.north;
+            configs.south = grid.configs.south;
+            configs.east = grid.configs.east;
+            configs.west = grid.configs.west;
+            configs.resolution_x = grid.configs.resolution_x;
+            configs.resolution_y = grid.configs.resolution_y;
+        } else {
+            if factor < 2 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The disaggregation factor must be at least 2.",
+                ));
+            }
+            configs.rows = input.configs.rows * factor as usize;
+            configs.columns = input.configs.columns * factor as usize;
+            configs.resolution_x = input.configs.resolution_x / factor as f64;
+            configs.resolution_y = input.configs.resolution_y / factor as f64;
+        }
+        if divide {
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            if configs.data_type != DataType::F32 && configs.data_type != DataType::F64 {
+                configs.data_type = DataType::F32;
+            }
+        }
+        let rows = configs.rows as isize;
+        let columns = configs.columns as isize;
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+
+        let get_weight = |row: isize, col: isize| -> f64 {
+            match &weights {
+                Some(w) => {
+                    let v = w.get_value(row, col);
+                    if is_nodata(v, w.configs.nodata) || v < 0f64 {
+                        0f64
+                    } else {
+                        v
+                    }
+                }
+                None => 1f64,
+            }
+        };
+
+        // Map each fine cell onto the coarse cell that contains its centre and, for divided
+        // values, accumulate the number of fine cells and total weight within each coarse cell.
+        let coarse_rows = input.configs.rows as isize;
+        let coarse_columns = input.configs.columns as isize;
+        let mut parent: Array2D<isize> = Array2D::new(rows, columns, -1isize, -1isize)?;
+        let mut cell_count: Array2D<f64> = Array2D::new(coarse_rows, coarse_columns, 0f64, -1f64)?;
+        let mut weight_sum: Array2D<f64> = Array2D::new(coarse_rows, coarse_columns, 0f64, -1f64)?;
+        for row in 0..rows {
+            let y = output.get_y_from_row(row);
+            let r = input.get_row_from_y(y);
+            for col in 0..columns {
+                let x = output.get_x_from_column(col);
+                let c = input.get_column_from_x(x);
+                if r < 0 || r >= coarse_rows || c < 0 || c >= coarse_columns {
+                    continue;
+                }
+                parent.set_value(row, col, r * coarse_columns + c);
+                if divide {
+                    cell_count.increment(r, c, 1f64);
+                    weight_sum.increment(r, c, get_weight(row, col));
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Mapping cells", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let out_nodata = output.configs.nodata;
+        for row in 0..rows {
+            for col in 0..columns {
+                let p = parent.get_value(row, col);
+                if p < 0 {
+                    continue;
+                }
+                let (r, c) = (p / coarse_columns, p % coarse_columns);
+                let z = input.get_value(r, c);
+                if is_nodata(z, nodata) {
+                    output.set_value(row, col, out_nodata);
+                    continue;
+                }
+                let value = if divide {
+                    let total_weight = weight_sum.get_value(r, c);
+                    if total_weight > 0f64 {
+                        z * get_weight(row, col) / total_weight
+                    } else {
+                        z / cell_count.get_value(r, c)
+                    }
+                } else if weights.is_some() && get_weight(row, col) == 0f64 {
+                    0f64
+                } else {
+                    z
+                };
+                output.set_value(row, col, value);
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        if !weights_file.is_empty() {
+            output.add_metadata_entry(format!("Weights file: {}", weights_file));
+        }
+        output.add_metadata_entry(format!("Method: {}", if divide { "divide" } else { "replicate" }));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 13/11/2018
-Last Modified: 22/11/2018
+Last Modified: 16/10/2026
 License: MIT
 */
 extern crate kdtree;
@@ -30,6 +30,21 @@ const EPSILON: f64 = std::f64::EPSILON;
 /// to correct any topological errors resulting from the slight misalignment of nodes
 /// along shared boundaries in the vector coverage before performing the `Dissolve` operation.
 ///
+/// By default, each contiguous dissolved region is output as a separate, single-part feature.
+/// When the `--multi_part` flag is specified, all of the dissolved regions that share the same
+/// dissolve field value (or all regions, if no dissolve field is specified) are combined into a
+/// single multi-part output feature.
+///
+/// The optional `--aggregate` parameter can be used to summarize other fields of the input
+/// attribute table for each output feature. It is specified as a list of FIELD:FUNCTION pairs,
+/// separated by semicolons (e.g. `--aggregate="POP:sum;DENSITY:mean;NAME:concat"`). Supported
+/// functions include `sum`, `mean`, `min`, `max`, and `concat`. The `sum` and `mean` functions
+/// require numeric fields, while `min`, `max`, and `concat` can be applied to any field type;
+/// `concat` lists the distinct values of the field, separated by commas. Null values are ignored.
+/// Each aggregated field is appended to the output attribute table using a name derived from
+/// the input field name and function (e.g. `POP_SUM`). Input features are assigned to the
+/// output feature that contains their interior point.
+///
 /// # See Also
 /// `Clip`, `Erase`, `Polygonize`
 pub struct Dissolve {
@@ -93,6 +108,24 @@ impl Dissolve {
             optional: true,
         });
 
+        parameters.push(ToolParameter {
+            name: "Aggregated Fields".to_owned(),
+            flags: vec!["--aggregate".to_owned()],
+            description: "List of FIELD:FUNCTION pairs, separated by semicolons, where FUNCTION is one of sum, mean, min, max, or concat (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output multi-part features?".to_owned(),
+            flags: vec!["--multipart".to_owned(), "--multi_part".to_owned()],
+            description: "Combine all dissolved regions sharing a field value into a single multi-part feature.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let e = format!("{}", env::current_exe().unwrap().display());
         let mut parent = env::current_exe().unwrap();
@@ -107,7 +140,8 @@ impl Dissolve {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -input=layer1.shp --field=SIZE -o=out_file.shp --snap=0.0000001",
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -input=layer1.shp --field=SIZE -o=out_file.shp --snap=0.0000001
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -input=layer1.shp --field=COUNTY -o=out_file.shp --aggregate=\"POP:sum;AREA:mean;NAME:concat\" --multi_part",
             short_exe, name
         ).replace("*", &sep);
 
@@ -161,66 +195,24 @@ impl WhiteboxTool for Dissolve {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut dissolve_key = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let dissolve_key = parsed.get_string("field").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut precision = std::f64::EPSILON;
+        let aggregate_str = parsed.get_string("aggregate").unwrap_or_default();
+        let multi_part = parsed.get_bool("multi_part");
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val.contains("-input") {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                dissolve_key = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-snap" {
-                precision = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if precision == 0f64 {
-                    precision = std::f64::EPSILON;
-                }
+        if let Some(value) = parsed.get_f64("snap") {
+            precision = value;
+            if precision == 0f64 {
+                precision = std::f64::EPSILON;
             }
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -237,12 +229,8 @@ impl WhiteboxTool for Dissolve {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -291,6 +279,12 @@ impl WhiteboxTool for Dissolve {
                 .add_field(input.attributes.get_field(key_index));
         }
 
+        // parse the aggregation specifications and add their output fields
+        let aggregations = parse_aggregations(&aggregate_str, &input)?;
+        for agg in &aggregations {
+            output.attributes.add_field(&agg.out_field);
+        }
+
         // let num_decimals = 6;
         // let precision = 1f64 / num_decimals as f64;
 
@@ -310,6 +304,7 @@ impl WhiteboxTool for Dissolve {
         let mut last_point_in_part: usize;
         let mut att: FieldData;
         for record_num in 0..input.num_records {
+            reporter.check_cancelled()?;
             let record = input.get_record(record_num);
             att = if use_dissolve_key {
                 input.attributes.get_value(record_num, &dissolve_key)
@@ -445,10 +440,11 @@ impl WhiteboxTool for Dissolve {
                     pl.vertices.push(polygons[i][j]);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / num_polygons as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -487,10 +483,11 @@ impl WhiteboxTool for Dissolve {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -1103,10 +1100,11 @@ impl WhiteboxTool for Dissolve {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / num_polylines as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -1142,19 +1140,110 @@ impl WhiteboxTool for Dissolve {
             }
         }
 
-        for a in 0..feature_geometries.len() {
-            output.add_record(feature_geometries[a].clone());
-            if use_dissolve_key {
-                let att = attribute[poly_id[a]].clone();
-                output
-                    .attributes
-                    .add_record(vec![FieldData::Int(fid), att], false);
-            } else {
-                output
-                    .attributes
-                    .add_record(vec![FieldData::Int(fid)], false);
+        // Which input records contribute to each of the output features?
+        let mut feature_records: Vec<Vec<usize>> = vec![vec![]; feature_geometries.len()];
+        if !aggregations.is_empty() && !multi_part {
+            let mut hull_areas = Vec::with_capacity(feature_geometries.len());
+            for a in 0..feature_geometries.len() {
+                hull_areas.push(polygon_area(feature_hull(&feature_geometries[a])).abs());
+            }
+            let mut contributing: Vec<HashSet<usize>> =
+                vec![HashSet::new(); feature_geometries.len()];
+            for j in 0..num_polygons {
+                if is_part_a_hole[j] {
+                    continue;
+                }
+                let p = interior_point(&polygons[j].vertices);
+                let mut min_area = f64::INFINITY;
+                let mut min_index = feature_geometries.len();
+                for a in 0..feature_geometries.len() {
+                    if bb[a].is_point_in_box(p.x, p.y)
+                        && (!use_dissolve_key || attribute[poly_id[a]] == attribute[j])
+                        && hull_areas[a] < min_area
+                        && point_in_poly(&p, feature_hull(&feature_geometries[a]))
+                    {
+                        min_area = hull_areas[a];
+                        min_index = a;
+                    }
+                }
+                if min_index < feature_geometries.len() {
+                    contributing[min_index].insert(polygons[j].id);
+                }
+            }
+            for a in 0..feature_geometries.len() {
+                let mut records: Vec<usize> = contributing[a].iter().cloned().collect();
+                records.sort();
+                feature_records[a] = records;
+            }
+        }
+
+        if multi_part {
+            // group the features by their dissolve key, in order of first appearance
+            let mut group_keys: Vec<String> = vec![];
+            let mut group_features: Vec<Vec<usize>> = vec![];
+            for a in 0..feature_geometries.len() {
+                let key = if use_dissolve_key {
+                    format!("{}", attribute[poly_id[a]])
+                } else {
+                    String::new()
+                };
+                match group_keys.iter().position(|k| *k == key) {
+                    Some(g) => group_features[g].push(a),
+                    None => {
+                        group_keys.push(key);
+                        group_features.push(vec![a]);
+                    }
+                }
+            }
+            for g in 0..group_features.len() {
+                reporter.check_cancelled()?;
+                let mut sfg = ShapefileGeometry::new(ShapeType::Polygon);
+                for &a in &group_features[g] {
+                    let geom = &feature_geometries[a];
+                    for part in 0..geom.num_parts as usize {
+                        let start = geom.parts[part] as usize;
+                        let end = if part < geom.num_parts as usize - 1 {
+                            geom.parts[part + 1] as usize
+                        } else {
+                            geom.num_points as usize
+                        };
+                        sfg.add_part(&geom.points[start..end]);
+                    }
+                }
+                output.add_record(sfg);
+
+                let mut rec_atts = vec![FieldData::Int(fid)];
+                if use_dissolve_key {
+                    rec_atts.push(attribute[poly_id[group_features[g][0]]].clone());
+                }
+                if !aggregations.is_empty() {
+                    let records: Vec<usize> = (0..input.num_records)
+                        .filter(|&r| {
+                            !use_dissolve_key
+                                || format!("{}", input.attributes.get_value(r, &dissolve_key))
+                                    == group_keys[g]
+                        })
+                        .collect();
+                    for agg in &aggregations {
+                        rec_atts.push(agg.apply(&input, &records));
+                    }
+                }
+                output.attributes.add_record(rec_atts, false);
+                fid += 1;
+            }
+        } else {
+            for a in 0..feature_geometries.len() {
+                output.add_record(feature_geometries[a].clone());
+                let mut rec_atts = vec![FieldData::Int(fid)];
+                if use_dissolve_key {
+                    rec_atts.push(attribute[poly_id[a]].clone());
+                }
+                for agg in &aggregations {
+                    rec_atts.push(agg.apply(&input, &feature_records[a]));
+                }
+                output.attributes.add_record(rec_atts, false);
+                fid += 1;
             }
-            fid += 1;
         }
 
         if verbose {
@@ -1232,3 +1321,172 @@ fn first_node_id(polyline: usize) -> usize {
 fn last_node_id(polyline: usize) -> usize {
     polyline * 2 + 1
 }
+
+/// Returns the points of the outer ring of a dissolved feature.
+fn feature_hull(geom: &ShapefileGeometry) -> &[Point2D] {
+    if geom.num_parts > 1 {
+        &geom.points[0..geom.parts[1] as usize]
+    } else {
+        &geom.points
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AggregationFunction {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    Concat,
+}
+
+struct Aggregation {
+    field_name: String,
+    function: AggregationFunction,
+    out_field: AttributeField,
+}
+
+impl Aggregation {
+    /// Summarizes the values of the aggregated field for a set of input records.
+    fn apply(&self, input: &Shapefile, records: &[usize]) -> FieldData {
+        let values: Vec<FieldData> = records
+            .iter()
+            .map(|&r| input.attributes.get_value(r, &self.field_name))
+            .filter(|v| *v != FieldData::Null)
+            .collect();
+        if values.is_empty() {
+            return FieldData::Null;
+        }
+        let is_int = self.out_field.field_type == 'N' && self.out_field.decimal_count == 0;
+        match self.function {
+            AggregationFunction::Sum | AggregationFunction::Mean => {
+                let mut sum = 0f64;
+                for v in &values {
+                    sum += match v {
+                        FieldData::Int(i) => *i as f64,
+                        FieldData::Real(f) => *f,
+                        _ => 0f64,
+                    };
+                }
+                if self.function == AggregationFunction::Mean {
+                    FieldData::Real(sum / values.len() as f64)
+                } else if is_int {
+                    FieldData::Int(sum as i32)
+                } else {
+                    FieldData::Real(sum)
+                }
+            }
+            AggregationFunction::Min | AggregationFunction::Max => {
+                let mut best = values[0].clone();
+                for v in values.iter().skip(1) {
+                    let ord = match (v, &best) {
+                        (FieldData::Int(a), FieldData::Int(b)) => a.cmp(b),
+                        (FieldData::Real(a), FieldData::Real(b)) => {
+                            a.partial_cmp(b).unwrap_or(Ordering::Equal)
+                        }
+                        _ => format!("{}", v).cmp(&format!("{}", best)),
+                    };
+                    if (self.function == AggregationFunction::Min && ord == Ordering::Less)
+                        || (self.function == AggregationFunction::Max && ord == Ordering::Greater)
+                    {
+                        best = v.clone();
+                    }
+                }
+                best
+            }
+            AggregationFunction::Concat => {
+                let mut distinct: Vec<String> = vec![];
+                for v in &values {
+                    let s = format!("{}", v);
+                    if !distinct.contains(&s) {
+                        distinct.push(s);
+                    }
+                }
+                let s: String = distinct.join(", ").chars().take(254).collect();
+                FieldData::Text(s)
+            }
+        }
+    }
+}
+
+/// Parses a list of FIELD:FUNCTION pairs (e.g. "POP:sum;NAME:concat") into aggregations.
+fn parse_aggregations(spec: &str, input: &Shapefile) -> Result<Vec<Aggregation>, Error> {
+    let mut aggregations: Vec<Aggregation> = vec![];
+    for item in spec.split(|c| c == ';' || c == ',') {
+        let item = item.trim();
+        if item.is_empty() {
+            continue;
+        }
+        let pair: Vec<&str> = item.split(':').collect();
+        if pair.len() != 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unable to parse the aggregation '{}'; use the form FIELD:FUNCTION.", item),
+            ));
+        }
+        let field_name = pair[0].trim();
+        let (function, suffix) = match pair[1].trim().to_lowercase().as_str() {
+            "sum" => (AggregationFunction::Sum, "SUM"),
+            "mean" | "average" | "avg" => (AggregationFunction::Mean, "AVG"),
+            "min" | "minimum" => (AggregationFunction::Min, "MIN"),
+            "max" | "maximum" => (AggregationFunction::Max, "MAX"),
+            "concat" | "concatenate" => (AggregationFunction::Concat, "CAT"),
+            f => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unrecognized aggregation function '{}'. Use sum, mean, min, max, or concat.", f),
+                ))
+            }
+        };
+        let field = match input.attributes.get_field_num(field_name) {
+            Some(i) => input.attributes.get_field(i).clone(),
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The aggregated field '{}' does not exist in the input attribute table.", field_name),
+                ))
+            }
+        };
+        let is_numeric = field.field_type == 'N' || field.field_type == 'F';
+        if !is_numeric
+            && (function == AggregationFunction::Sum || function == AggregationFunction::Mean)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The sum and mean aggregations require a numeric field; '{}' is not numeric.", field_name),
+            ));
+        }
+
+        // DBF field names are limited to 10 characters.
+        let base: String = field.name.chars().take(6).collect();
+        let out_name = format!("{}_{}", base, suffix);
+        let out_field = match function {
+            AggregationFunction::Sum => {
+                let mut f = field.clone();
+                f.name = out_name;
+                f.field_length = f.field_length.max(18);
+                f
+            }
+            AggregationFunction::Mean => AttributeField::new(
+                &out_name,
+                FieldDataType::Real,
+                18u8,
+                field.decimal_count.max(4),
+            ),
+            AggregationFunction::Min | AggregationFunction::Max => {
+                let mut f = field.clone();
+                f.name = out_name;
+                f
+            }
+            AggregationFunction::Concat => {
+                AttributeField::new(&out_name, FieldDataType::Text, 254u8, 0u8)
+            }
+        };
+        aggregations.push(Aggregation {
+            field_name: field.name.clone(),
+            function: function,
+            out_field: out_field,
+        });
+    }
+    Ok(aggregations)
+}
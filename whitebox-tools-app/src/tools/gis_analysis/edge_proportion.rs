@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool will measure the edge proportion, i.e. the proportion of grid cells in a patch that are located along the
 /// patch's boundary, for an input raster image (`--input`). Edge proportion is an indicator of polygon shape complexity
@@ -135,46 +134,12 @@ impl WhiteboxTool for EdgeProportion {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut output_text = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-output_text"
-                || vec[0].to_lowercase() == "--output_text"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    output_text = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let output_text = parsed.get_bool("output_text");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -187,17 +152,11 @@ impl WhiteboxTool for EdgeProportion {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading input data...");
@@ -210,17 +169,15 @@ impl WhiteboxTool for EdgeProportion {
 
         let start = Instant::now();
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut num_cells = vec![0usize; max_val + 1];
                 let mut num_edge_cells = vec![0usize; max_val + 1];
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
@@ -230,9 +187,12 @@ impl WhiteboxTool for EdgeProportion {
                 let mut is_edge: bool;
                 let mut bin: usize;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z > 0f64 && z != nodata {
+                        if z > 0f64 && !is_nodata(z, nodata) {
                             bin = z.floor() as usize;
                             num_cells[bin] += 1;
                             is_edge = false;
@@ -249,7 +209,9 @@ impl WhiteboxTool for EdgeProportion {
                         }
                     }
                 }
-                tx.send((num_cells, num_edge_cells)).unwrap();
+                if tx.send((num_cells, num_edge_cells)).is_err() {
+                    return;
+                }
             });
         }
 
@@ -261,10 +223,11 @@ impl WhiteboxTool for EdgeProportion {
                 num_cells[bin] += vec1[bin];
                 num_edge_cells[bin] += vec2[bin];
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tid as f64 / (num_procs - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -283,19 +246,21 @@ impl WhiteboxTool for EdgeProportion {
             let input = input.clone();
             let edge_props = edge_props.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut bin: usize;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z > 0f64 && z != nodata {
+                        if z > 0f64 && !is_nodata(z, nodata) {
                             bin = z.floor() as usize;
                             data[col as usize] = edge_props[bin];
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -308,10 +273,11 @@ impl WhiteboxTool for EdgeProportion {
         for r in 0..rows {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -340,7 +306,7 @@ impl WhiteboxTool for EdgeProportion {
         if output_text {
             println!("Edge Proportion\nPatch ID\tValue");
             for bin in 0..max_val + 1 {
-                if edge_props[bin] > 0f64 && edge_props[bin] != nodata {
+                if edge_props[bin] > 0f64 && !is_nodata(edge_props[bin], nodata) {
                     println!("{}\t{}", bin, edge_props[bin]);
                 }
             }
@@ -65,7 +65,7 @@ impl EliminateCoincidentPoints {
 
         parameters.push(ToolParameter {
             name: "Distance Tolerance".to_owned(),
-            flags: vec!["--tolerance".to_owned()],
+            flags: vec!["--tol".to_owned(), "--tolerance".to_owned()],
             description: "The distance tolerance for points.".to_owned(),
             parameter_type: ParameterType::Float,
             default_value: None,
@@ -140,56 +140,15 @@ impl WhiteboxTool for EliminateCoincidentPoints {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut tolerance = 0f64;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let mut tolerance = parsed.get_f64("tolerance").unwrap_or(0f64);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val.contains("tol") {
-                tolerance = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -213,13 +172,9 @@ impl WhiteboxTool for EliminateCoincidentPoints {
             ));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         // Get the spatial extent
         let input = Shapefile::read(&input_file)?;
@@ -251,10 +206,11 @@ impl WhiteboxTool for EliminateCoincidentPoints {
             y = record.points[0].y;
             frs.insert(x, y, record_num);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * record_num as f64 / (num_points - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Building fixed-radius search: {}%", progress);
+                    reporter.progress("Building fixed-radius search", progress);
                     old_progress = progress;
                 }
             }
@@ -278,10 +234,11 @@ impl WhiteboxTool for EliminateCoincidentPoints {
                 let atts = input.attributes.get_record(record_num);
                 output.attributes.add_record(atts.clone(), false);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * record_num as f64 / (num_points - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
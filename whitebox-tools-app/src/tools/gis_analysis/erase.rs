@@ -16,7 +16,6 @@ use crate::tools::*;
 use whitebox_vector::*;
 use kdtree::distance::squared_euclidean;
 use kdtree::KdTree;
-use num_cpus;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashSet};
 use std::env;
@@ -24,7 +23,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 const EPSILON: f64 = std::f64::EPSILON;
 
@@ -154,50 +153,15 @@ impl WhiteboxTool for Erase {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut erase_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut erase_file = parsed.get_string("erase").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val.contains("-input") {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-erase" {
-                erase_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -214,15 +178,9 @@ impl WhiteboxTool for Erase {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !erase_file.contains(&sep) && !erase_file.contains("/") {
-            erase_file = format!("{}{}", working_directory, erase_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        erase_file = resolve_input_path(&erase_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -245,6 +203,7 @@ impl WhiteboxTool for Erase {
         let mut first_point_in_part: usize;
         let mut last_point_in_part: usize;
         for record_num in 0..erase.num_records {
+            reporter.check_cancelled()?;
             let record = erase.get_record(record_num);
             for part in 0..record.num_parts as usize {
                 first_point_in_part = record.parts[part] as usize;
@@ -289,7 +248,7 @@ impl WhiteboxTool for Erase {
         let erase_bb = Arc::new(erase_bb);
         let is_erase_part_a_hole = Arc::new(is_erase_part_a_hole);
 
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
 
         match input.header.shape_type.base_shape_type() {
@@ -301,7 +260,7 @@ impl WhiteboxTool for Erase {
                     let erase_polylines = erase_polylines.clone();
                     let is_erase_part_a_hole = is_erase_part_a_hole.clone();
                     let tx = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         let mut p: Point2D;
                         let mut out: bool;
                         for record_num in (0..input.num_records).filter(|r| r % num_procs == tid) {
@@ -319,7 +278,9 @@ impl WhiteboxTool for Erase {
                                     }
                                 }
                             }
-                            tx.send((record_num, out)).unwrap();
+                            if tx.send((record_num, out)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -330,10 +291,11 @@ impl WhiteboxTool for Erase {
                     if !out {
                         output_feature[record_num] = true;
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * r as f64 / (input.num_records - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -356,10 +318,11 @@ impl WhiteboxTool for Erase {
                                 .add_record(input.attributes.get_record(r).clone(), false)
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * r as f64 / (input.num_records - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -378,7 +341,7 @@ impl WhiteboxTool for Erase {
                         let erase_polylines = erase_polylines.clone();
                         let is_erase_part_a_hole = is_erase_part_a_hole.clone();
                         let tx = tx.clone();
-                        thread::spawn(move || {
+                        spawn(move || {
                             let mut p: Point2D;
                             let mut out: bool;
                             for point_num in (0..num_points).filter(|r| r % num_procs == tid) {
@@ -395,7 +358,9 @@ impl WhiteboxTool for Erase {
                                         }
                                     }
                                 }
-                                tx.send((point_num, out)).unwrap();
+                                if tx.send((point_num, out)).is_err() {
+                                    return;
+                                }
                             }
                         });
                     }
@@ -454,11 +419,12 @@ impl WhiteboxTool for Erase {
                         }
                     }
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * record_num as f64 / (input.num_records - 1) as f64)
                             as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -474,6 +440,7 @@ impl WhiteboxTool for Erase {
                 let mut features_bb: Vec<BoundingBox> = vec![];
                 let mut features_polylines: Vec<Polyline> = vec![];
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -498,6 +465,7 @@ impl WhiteboxTool for Erase {
 
                 // hunt for intersections in the overlapping bounding boxes
                 for record_num1 in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     for record_num2 in 0..erase_polylines.len() {
                         if features_bb[record_num1].overlaps(erase_bb[record_num2]) {
                             // find any intersections between the polylines
@@ -554,12 +522,13 @@ impl WhiteboxTool for Erase {
                         }
                     }
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (record_num1 + 1) as f64
                             / features_polylines.len() as f64)
                             as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -1582,11 +1551,12 @@ impl WhiteboxTool for Erase {
                         }
                     }
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64)
                             as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
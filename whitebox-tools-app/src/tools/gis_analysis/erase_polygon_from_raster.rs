@@ -2,13 +2,12 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 27/03/2018
-Last Modified: 13/10/2018
+Last Modified: 16/10/2026
 License: MIT
 */
 
-use whitebox_common::algorithms;
+use super::raster_mask::{apply_polygon_mask, MaskOptions};
 use whitebox_raster::*;
-use whitebox_common::structures::Point2D;
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
 use std::env;
@@ -21,6 +20,11 @@ use std::path;
 /// polygon holes will not be removed from the output raster. Raster grid cells that fall inside of a polygons in
 /// the erase file will be assigned the NoData background value in the output file.
 ///
+/// The erase mask may be grown or shrunk by a distance, in map units, using the `--buffer` parameter, e.g.
+/// `--buffer=10.0` also erases cells within 10 map units of a polygon. The `--inverse` flag reverses the sense of
+/// the mask, such that only the cells inside of the polygons are retained; unlike `ClipRasterToPolygon`, the
+/// output raster always has the same dimensions as the input raster.
+///
 /// # See Also
 /// `ClipRasterToPolygon`
 pub struct ErasePolygonFromRaster {
@@ -50,7 +54,7 @@ impl ErasePolygonFromRaster {
 
         parameters.push(ToolParameter {
             name: "Input Vector Polygon File".to_owned(),
-            flags: vec!["--polygons".to_owned()],
+            flags: vec!["--polygon".to_owned(), "--polygons".to_owned()],
             description: "Input vector polygons file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
                 VectorGeometryType::Polygon,
@@ -68,6 +72,24 @@ impl ErasePolygonFromRaster {
             optional: false,
         });
 
+        parameters.push(ToolParameter {
+            name: "Buffer Distance".to_owned(),
+            flags: vec!["--buffer".to_owned()],
+            description: "Distance by which the erase mask is grown (positive) or shrunk (negative), in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Inverse mask?".to_owned(),
+            flags: vec!["--inverse".to_owned()],
+            description: "Erase cells outside of the polygons rather than inside?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
         let sep: String = path::MAIN_SEPARATOR.to_string();
         let e = format!("{}", env::current_exe().unwrap().display());
         let mut parent = env::current_exe().unwrap();
@@ -126,47 +148,14 @@ impl WhiteboxTool for ErasePolygonFromRaster {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut polygons_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-polygon" || flag_val == "-polygons" {
-                polygons_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut polygons_file = parsed.get_string("polygons").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let buffer = parsed.get_f64("buffer").unwrap_or(0f64);
+        let inverse = parsed.get_bool("inverse");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -179,20 +168,9 @@ impl WhiteboxTool for ErasePolygonFromRaster {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !polygons_file.contains(&sep) && !polygons_file.contains("/") {
-            polygons_file = format!("{}{}", working_directory, polygons_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        polygons_file = resolve_input_path(&polygons_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -200,13 +178,10 @@ impl WhiteboxTool for ErasePolygonFromRaster {
         let input = Raster::new(&input_file, "r")?;
 
         let start = Instant::now();
-        let rows = input.configs.rows as isize;
-        let columns = input.configs.columns as isize;
-        let nodata = input.configs.nodata;
 
         let polygons = Shapefile::read(&polygons_file)?;
 
-        // make sure the input vector file is of points type
+        // make sure the input vector file is of polygons type
         if polygons.header.shape_type.base_shape_type() != ShapeType::Polygon {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
@@ -214,153 +189,15 @@ impl WhiteboxTool for ErasePolygonFromRaster {
             ));
         }
 
-        let mut output = Raster::initialize_using_file(&output_file, &input);
-        match output.set_data_from_raster(&input) {
-            Ok(_) => (), // do nothings
-            Err(err) => return Err(err),
-        }
-
-        let mut start_point_in_part: usize;
-        let mut end_point_in_part: usize;
-        let (mut row, mut col): (isize, isize);
-        let (mut x, mut y): (f64, f64);
-        let (mut starting_row, mut ending_row, mut starting_col, mut ending_col): (
-            isize,
-            isize,
-            isize,
-            isize,
-        );
-        let num_records = polygons.num_records;
-        for record_num in 0..polygons.num_records {
-            let record = polygons.get_record(record_num);
-
-            let mut part_num = 1;
-            for part in 0..record.num_parts as usize {
-                if !record.is_hole(part as i32) {
-                    // erase cells from this part
-
-                    start_point_in_part = record.parts[part] as usize;
-                    end_point_in_part = if part < record.num_parts as usize - 1 {
-                        record.parts[part + 1] as usize - 1
-                    } else {
-                        record.num_points as usize - 1
-                    };
-
-                    // First, figure out the minimum and maximum row and column for the polygon part
-                    starting_row = rows;
-                    ending_row = 0;
-                    starting_col = columns;
-                    ending_col = 0;
-                    for p in start_point_in_part..end_point_in_part + 1 {
-                        row = input.get_row_from_y(record.points[p].y);
-                        col = input.get_column_from_x(record.points[p].x);
-                        if row < starting_row {
-                            starting_row = row;
-                        }
-                        if row > ending_row {
-                            ending_row = row;
-                        }
-                        if col < starting_col {
-                            starting_col = col;
-                        }
-                        if col > ending_col {
-                            ending_col = col;
-                        }
-                    }
-
-                    for r in starting_row..ending_row {
-                        y = input.get_y_from_row(r);
-                        for c in starting_col..ending_col {
-                            x = input.get_x_from_column(c);
-                            if algorithms::point_in_poly(
-                                &Point2D { x: x, y: y },
-                                &record.points[start_point_in_part..end_point_in_part + 1],
-                            ) {
-                                output.set_value(r, c, nodata);
-                            }
-                        }
-                        if verbose {
-                            progress = (100.0_f64 * r as f64 / (ending_row - starting_row) as f64)
-                                as usize;
-                            if progress != old_progress {
-                                println!(
-                                    "Progress (rec {} of {} part {}): {}%",
-                                    record_num + 1,
-                                    num_records,
-                                    part_num,
-                                    progress
-                                );
-                                old_progress = progress;
-                            }
-                        }
-                    }
-                    part_num += 1;
-                }
-            }
-
-            for part in 0..record.num_parts as usize {
-                if record.is_hole(part as i32) {
-                    // add cells from this part back in
-
-                    start_point_in_part = record.parts[part] as usize;
-                    end_point_in_part = if part < record.num_parts as usize - 1 {
-                        record.parts[part + 1] as usize - 1
-                    } else {
-                        record.num_points as usize - 1
-                    };
-
-                    // First, figure out the minimum and maximum row and column for the polygon part
-                    starting_row = rows;
-                    ending_row = 0;
-                    starting_col = columns;
-                    ending_col = 0;
-                    for p in start_point_in_part..end_point_in_part + 1 {
-                        row = input.get_row_from_y(record.points[p].y);
-                        col = input.get_column_from_x(record.points[p].x);
-                        if row < starting_row {
-                            starting_row = row;
-                        }
-                        if row > ending_row {
-                            ending_row = row;
-                        }
-                        if col < starting_col {
-                            starting_col = col;
-                        }
-                        if col > ending_col {
-                            ending_col = col;
-                        }
-                    }
-
-                    for r in starting_row..ending_row {
-                        y = input.get_y_from_row(r);
-                        for c in starting_col..ending_col {
-                            x = input.get_x_from_column(c);
-                            if algorithms::point_in_poly(
-                                &Point2D { x: x, y: y },
-                                &record.points[start_point_in_part..end_point_in_part + 1],
-                            ) {
-                                output.set_value(r, c, input.get_value(r, c));
-                            }
-                        }
-                        if verbose {
-                            progress = (100.0_f64 * r as f64 / (ending_row - starting_row) as f64)
-                                as usize;
-                            if progress != old_progress {
-                                println!(
-                                    "Progress (rec {} of {} part {}): {}%",
-                                    record_num + 1,
-                                    num_records,
-                                    part_num,
-                                    progress
-                                );
-                                old_progress = progress;
-                            }
-                        }
-                    }
-                    part_num += 1;
-                }
-            }
-        }
+        // Erasing is clipping with an inverted mask over the full input extent.
+        let options = MaskOptions {
+            inverse: !inverse,
+            buffer: buffer,
+            crop: false,
+        };
+        let records: Vec<usize> = (0..polygons.num_records).collect();
+        let mut output =
+            apply_polygon_mask(&input, &polygons, &records, &options, &output_file, verbose, reporter)?;
 
         let elapsed_time = get_formatted_elapsed_time(start);
         output.add_metadata_entry(format!(
@@ -368,6 +205,10 @@ impl WhiteboxTool for ErasePolygonFromRaster {
             self.get_tool_name()
         ));
         output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Polygons file: {}", polygons_file));
+        if buffer != 0f64 {
+            output.add_metadata_entry(format!("Buffer distance: {}", buffer));
+        }
         output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
 
         if verbose {
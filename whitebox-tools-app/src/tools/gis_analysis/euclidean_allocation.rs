@@ -11,7 +11,7 @@ use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool assigns grid cells in the output image the value of the nearest target cell in
@@ -123,39 +123,11 @@ impl WhiteboxTool for EuclideanAllocation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -168,17 +140,11 @@ impl WhiteboxTool for EuclideanAllocation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -210,7 +176,7 @@ impl WhiteboxTool for EuclideanAllocation {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata(z, nodata) {
                     distance[(row, col)] = 0.0;
                     allocation[(row, col)] = input[(row, col)];
                 } else {
@@ -218,10 +184,11 @@ impl WhiteboxTool for EuclideanAllocation {
                     allocation[(row, col)] = inf_val;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Initializing Rasters: {}%", progress);
+                    reporter.progress("Initializing Rasters", progress);
                     old_progress = progress;
                 }
             }
@@ -237,7 +204,7 @@ impl WhiteboxTool for EuclideanAllocation {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = distance[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata(z2, nodata) {
                             h = match i {
                                 0 => 2.0 * r_x[(y, x)] + 1.0,
                                 1 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
@@ -261,10 +228,11 @@ impl WhiteboxTool for EuclideanAllocation {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (1 of 3): {}%", progress);
+                    reporter.progress("Progress (1 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -280,7 +248,7 @@ impl WhiteboxTool for EuclideanAllocation {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = distance[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata(z2, nodata) {
                             h = match i {
                                 5 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
                                 4 => 2.0 * r_x[(y, x)] + 1.0,
@@ -304,10 +272,11 @@ impl WhiteboxTool for EuclideanAllocation {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (rows - row) as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (2 of 3): {}%", progress);
+                    reporter.progress("Progress (2 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -316,14 +285,15 @@ impl WhiteboxTool for EuclideanAllocation {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata(z, nodata) {
                     allocation[(row, col)] = nodata;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (3 of 3): {}%", progress);
+                    reporter.progress("Progress (3 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -11,7 +11,7 @@ use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool will estimate the Euclidean distance (i.e. straight-line distance) between each
@@ -132,40 +132,11 @@ impl WhiteboxTool for EuclideanDistance {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -178,17 +149,11 @@ impl WhiteboxTool for EuclideanDistance {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -221,16 +186,17 @@ impl WhiteboxTool for EuclideanDistance {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata(z, nodata) {
                     output.set_value(row, col, 0.0);
                 } else {
                     output.set_value(row, col, inf_val);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Initializing Rasters: {}%", progress);
+                    reporter.progress("Initializing Rasters", progress);
                     old_progress = progress;
                 }
             }
@@ -246,7 +212,7 @@ impl WhiteboxTool for EuclideanDistance {
                         x = col + dx[i];
                         y = row + dy[i];
                         z2 = output.get_value(y, x);
-                        if z2 != nodata {
+                        if !is_nodata(z2, nodata) {
                             h = match i {
                                 0 => 2.0 * rx.get_value(y, x) + 1.0,
                                 1 => 2.0 * (rx.get_value(y, x) + ry.get_value(y, x) + 1.0),
@@ -269,10 +235,11 @@ impl WhiteboxTool for EuclideanDistance {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (1 of 3): {}%", progress);
+                    reporter.progress("Progress (1 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -288,7 +255,7 @@ impl WhiteboxTool for EuclideanDistance {
                         x = col + dx[i];
                         y = row + dy[i];
                         z2 = output.get_value(y, x);
-                        if z2 != nodata {
+                        if !is_nodata(z2, nodata) {
                             h = match i {
                                 5 => 2.0 * (rx.get_value(y, x) + ry.get_value(y, x) + 1.0),
                                 4 => 2.0 * rx.get_value(y, x) + 1.0,
@@ -311,10 +278,11 @@ impl WhiteboxTool for EuclideanDistance {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (rows - row) as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (2 of 3): {}%", progress);
+                    reporter.progress("Progress (2 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -323,16 +291,17 @@ impl WhiteboxTool for EuclideanDistance {
         let cell_size = (input.configs.resolution_x + input.configs.resolution_y) / 2.0;
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) != nodata {
+                if !is_nodata(input.get_value(row, col), nodata) {
                     output.set_value(row, col, output.get_value(row, col).sqrt() * cell_size);
                 } else {
                     output.set_value(row, col, nodata);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (3 of 3): {}%", progress);
+                    reporter.progress("Progress (3 of 3)", progress);
                     old_progress = progress;
                 }
             }
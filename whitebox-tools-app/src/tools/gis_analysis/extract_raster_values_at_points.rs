@@ -137,45 +137,12 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files = String::new();
-        let mut points_file = String::new();
-        let mut output_text = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-points" {
-                points_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val.contains("-out_text") {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    output_text = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_string("inputs").unwrap_or_default();
+        let points_file = parsed.get_string("points").unwrap_or_default();
+        let output_text = parsed.get_bool("out_text");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -188,8 +155,6 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let start = Instant::now();
 
         let mut cmd = input_files.split(";");
@@ -221,6 +186,7 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
         let mut y_vals = Vec::with_capacity(num_records);
         let mut raster_values = vec![vec![0f64; num_files]; num_records];
         for record_num in 0..num_records {
+            reporter.check_cancelled()?;
             let record = points.get_record(record_num);
             y_vals.push(record.points[0].y);
             x_vals.push(record.points[0].x);
@@ -244,9 +210,7 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
                 };
 
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 let input = Raster::new(&input_file, "r")?;
 
                 for record_num in 0..num_records {
@@ -271,6 +235,7 @@ impl WhiteboxTool for ExtractRasterValuesAtPoints {
         if output_text {
             println!("Point values:");
             for record_num in 0..num_records {
+                reporter.check_cancelled()?;
                 println!(
                     "Point {} values: {:?}",
                     record_num + 1,
@@ -0,0 +1,533 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::structures::Array2D;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool fills the *NoData* gaps in a primary raster (`--input`) using the values of one or more
+/// secondary rasters (`--fill_rasters`), which are listed in priority order. Each *NoData* cell in the
+/// primary raster receives the value of the first secondary raster that contains valid data at that
+/// location; cells that are *NoData* in all of the inputs remain *NoData* in the output. The tool is
+/// intended for patching the gaps in a lidar-derived DEM (e.g. water bodies, dense vegetation, or areas
+/// beyond the survey boundary) with coarser regional or national DEMs.
+///
+/// The secondary rasters need not share the grid of the primary raster. Any fill raster that differs in
+/// extent or resolution is resampled to the primary grid, using bilinear interpolation for continuous
+/// floating-point data and nearest-neighbour interpolation otherwise, and a warning is issued. The output
+/// raster always has the grid of the primary input.
+///
+/// Secondary DEMs commonly differ from the primary DEM by a vertical offset, e.g. because of datum
+/// differences or canopy bias, which produces a step at the edge of each filled gap. The `--harmonize`
+/// parameter controls how this offset is removed. With `none`, fill values are used as is. With `global`,
+/// a single offset is estimated for each fill raster, as the median difference between the primary and
+/// secondary values over all of the valid primary cells lying within `--edge_width` grid cells of a filled
+/// gap. With `local` (the default), an offset is estimated separately for each gap, i.e. each connected
+/// (8-neighbour) patch of *NoData* cells in the primary raster, and each fill raster used within it, from
+/// the primary cells surrounding that gap. Gaps for which no offset can be estimated (e.g. gaps that do not
+/// border any valid primary data) fall back onto the global offset of the fill raster.
+///
+/// The optional `--out_source` raster records the origin of each output value, with 0 indicating the
+/// primary raster and 1, 2, 3... the position of the fill raster within the `--fill_rasters` list.
+///
+/// # See Also
+/// `UpdateNodataCells`, `Mosaic`, `MosaicWithFeathering`, `FillMissingData`
+pub struct FillNodataFromRasters {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl FillNodataFromRasters {
+    pub fn new() -> FillNodataFromRasters {
+        // public constructor
+        let name = "FillNodataFromRasters".to_string();
+        let toolbox = "GIS Analysis/Overlay Tools".to_string();
+        let description =
+            "Fills NoData gaps in a raster using one or more overlapping rasters in priority order, with optional vertical-offset harmonization.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input primary raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Fill Files".to_owned(),
+            flags: vec!["--fill".to_owned(), "--fill_rasters".to_owned()],
+            description: "Input fill raster files, in priority order.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Offset Harmonization".to_owned(),
+            flags: vec!["--harmonize".to_owned()],
+            description: "Vertical-offset harmonization method; options are 'none', 'global', and 'local'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "none".to_owned(),
+                "global".to_owned(),
+                "local".to_owned(),
+            ]),
+            default_value: Some("local".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Edge Width (cells)".to_owned(),
+            flags: vec!["--edge_width".to_owned()],
+            description: "Width, in grid cells, of the band of primary data around each gap used to estimate offsets.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Source File (optional)".to_owned(),
+            flags: vec!["--out_source".to_owned()],
+            description: "Optional output raster recording the input from which each cell's value was taken.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=lidar_dem.tif --fill_rasters='srtm.tif;national_dem.tif' -o=output.tif --harmonize=local --edge_width=5 --out_source=source.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        FillNodataFromRasters {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for FillNodataFromRasters {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let fill_files = parsed.get_string("fill_rasters").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut harmonize = String::from("local");
+        let mut edge_width = parsed.get_i64("edge_width").map(|v| v as isize).unwrap_or(3isize);
+        let mut source_file = parsed.get_string("out_source").unwrap_or_default();
+
+        if let Some(value) = parsed.get_string("harmonize") {
+            harmonize = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if !harmonize.contains("none") && !harmonize.contains("global") && !harmonize.contains("local") {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --harmonize parameter must be one of 'none', 'global', or 'local'.",
+            ));
+        }
+        let harmonize_global = harmonize.contains("global");
+        let harmonize_local = harmonize.contains("local");
+        if edge_width < 1 {
+            edge_width = 1;
+        }
+
+        for file in [&mut input_file, &mut output_file, &mut source_file] {
+            *file = resolve_path(file, working_directory);
+        }
+
+        let mut cmd = fill_files.split(";");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = fill_files.split(",");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+        let mut fill_file_names = vec![];
+        for value in vec {
+            if !value.trim().is_empty() {
+                let mut file = value.trim().to_owned();
+                file = resolve_input_path(&file, working_directory)?;
+                fill_file_names.push(file);
+            }
+        }
+        if fill_file_names.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one fill raster must be specified.",
+            ));
+        }
+        if fill_file_names.len() > i16::MAX as usize {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Too many fill rasters were specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let mut fills = Vec::with_capacity(fill_file_names.len());
+        for file in &fill_file_names {
+            fills.push(read_secondary_raster(file, &input, true, true, verbose)?);
+        }
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+        // Label each gap, i.e. each 8-connected patch of NoData cells in the primary raster.
+        let mut gap_id: Array2D<u32> = Array2D::new(rows, columns, 0u32, 0u32)?;
+        let mut num_gaps = 0u32;
+        let mut stack = vec![];
+        for row in 0..rows {
+            for col in 0..columns {
+                if is_nodata(input.get_value(row, col), nodata) && gap_id.get_value(row, col) == 0 {
+                    num_gaps += 1;
+                    gap_id.set_value(row, col, num_gaps);
+                    stack.push((row, col));
+                    while let Some((r, c)) = stack.pop() {
+                        for n in 0..8 {
+                            let (rn, cn) = (r + dy[n], c + dx[n]);
+                            if rn >= 0
+                                && rn < rows
+                                && cn >= 0
+                                && cn < columns
+                                && gap_id.get_value(rn, cn) == 0
+                                && is_nodata(input.get_value(rn, cn), nodata)
+                            {
+                                gap_id.set_value(rn, cn, num_gaps);
+                                stack.push((rn, cn));
+                            }
+                        }
+                    }
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Labelling gaps", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Assign each gap cell to the highest-priority fill raster with valid data.
+        let mut source: Array2D<i16> = Array2D::new(rows, columns, 0i16, -1i16)?;
+        let mut num_filled = vec![0usize; fills.len()];
+        let mut num_unfilled = 0usize;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                if gap_id.get_value(row, col) > 0 {
+                    source.set_value(row, col, -1i16);
+                    for k in 0..fills.len() {
+                        if !is_nodata(fills[k].get_value(row, col), fills[k].configs.nodata) {
+                            source.set_value(row, col, (k + 1) as i16);
+                            num_filled[k] += 1;
+                            break;
+                        }
+                    }
+                    if source.get_value(row, col) == -1i16 {
+                        num_unfilled += 1;
+                    }
+                }
+            }
+        }
+
+        // Estimate the vertical offsets from the band of primary data around each gap.
+        let mut global_offsets = vec![0f64; fills.len()];
+        let mut local_offsets: HashMap<(u32, usize), f64> = HashMap::new();
+        if harmonize_global || harmonize_local {
+            let mut global_diffs: Vec<Vec<f64>> = vec![vec![]; fills.len()];
+            let mut local_diffs: HashMap<(u32, usize), Vec<f64>> = HashMap::new();
+            let mut sampled: HashSet<(isize, isize, u32, usize)> = HashSet::new();
+            for row in 0..rows {
+                for col in 0..columns {
+                    let id = gap_id.get_value(row, col);
+                    if id == 0 {
+                        continue;
+                    }
+                    let src = source.get_value(row, col);
+                    if src < 1 {
+                        continue;
+                    }
+                    let k = (src - 1) as usize;
+                    // only gap cells along the edge of the gap need to be considered
+                    let mut on_edge = false;
+                    for n in 0..8 {
+                        let (rn, cn) = (row + dy[n], col + dx[n]);
+                        if !is_nodata(input.get_value(rn, cn), nodata) {
+                            on_edge = true;
+                            break;
+                        }
+                    }
+                    if !on_edge {
+                        continue;
+                    }
+                    for r in (row - edge_width)..=(row + edge_width) {
+                        for c in (col - edge_width)..=(col + edge_width) {
+                            let z = input.get_value(r, c);
+                            if is_nodata(z, nodata) || sampled.contains(&(r, c, id, k)) {
+                                continue;
+                            }
+                            let zf = fills[k].get_value(r, c);
+                            if is_nodata(zf, fills[k].configs.nodata) {
+                                continue;
+                            }
+                            sampled.insert((r, c, id, k));
+                            global_diffs[k].push(z - zf);
+                            local_diffs.entry((id, k)).or_insert(vec![]).push(z - zf);
+                        }
+                    }
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Estimating offsets", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            for k in 0..fills.len() {
+                if let Some(m) = median(&mut global_diffs[k]) {
+                    global_offsets[k] = m;
+                }
+            }
+            if harmonize_local {
+                for (key, diffs) in local_diffs.iter_mut() {
+                    if let Some(m) = median(diffs) {
+                        local_offsets.insert(*key, m);
+                    }
+                }
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        for row in 0..rows {
+            for col in 0..columns {
+                let src = source.get_value(row, col);
+                if src == 0 {
+                    output.set_value(row, col, input.get_value(row, col));
+                } else if src > 0 {
+                    let k = (src - 1) as usize;
+                    let offset = if harmonize_local {
+                        match local_offsets.get(&(gap_id.get_value(row, col), k)) {
+                            Some(v) => *v,
+                            None => global_offsets[k],
+                        }
+                    } else if harmonize_global {
+                        global_offsets[k]
+                    } else {
+                        0f64
+                    };
+                    output.set_value(row, col, fills[k].get_value(row, col) + offset);
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Filling gaps", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Number of gaps: {}", num_gaps);
+            for k in 0..fills.len() {
+                if harmonize_global || harmonize_local {
+                    println!(
+                        "{}: {} cells filled (global offset {:.4})",
+                        fills[k].get_short_filename(),
+                        num_filled[k],
+                        global_offsets[k]
+                    );
+                } else {
+                    println!(
+                        "{}: {} cells filled",
+                        fills[k].get_short_filename(),
+                        num_filled[k]
+                    );
+                }
+            }
+            println!("Cells remaining NoData: {}", num_unfilled);
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        for k in 0..fills.len() {
+            output.add_metadata_entry(format!(
+                "Fill file {}: {} (offset {})",
+                k + 1,
+                fill_file_names[k],
+                global_offsets[k]
+            ));
+        }
+        output.add_metadata_entry(format!("Harmonization: {}", harmonize));
+        output.add_metadata_entry(format!("Edge width: {}", edge_width));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !source_file.is_empty() {
+            let mut src_out = Raster::initialize_using_file(&source_file, &input);
+            src_out.configs.data_type = DataType::I16;
+            src_out.configs.nodata = -1f64;
+            src_out.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            src_out.reinitialize_values(-1f64);
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    src_out.set_value(row, col, source.get_value(row, col) as f64);
+                }
+            }
+            src_out.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            src_out.add_metadata_entry(
+                "Values: 0 = primary input; n = nth fill raster".to_string(),
+            );
+            let _ = match src_out.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Source file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Median of a list of values, or `None` if the list is empty.
+fn median(values: &mut Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let n = values.len();
+    Some(if n % 2 == 1 {
+        values[n / 2]
+    } else {
+        (values[n / 2 - 1] + values[n / 2]) / 2f64
+    })
+}
@@ -11,7 +11,7 @@ use crate::tools::*;
 use rayon::prelude::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool takes an input raster (`--input`) containing integer-labelled features, such as the output of the `Clump` tool,
@@ -70,7 +70,7 @@ impl FilterRasterFeaturesByArea {
             flags: vec!["--background".to_owned()],
             description: "Background value.".to_owned(),
             parameter_type: ParameterType::OptionList(vec!["zero".to_owned(), "nodata".to_owned()]),
-            default_value: Some("zero".to_owned()),
+            default_value: Some("nodata".to_owned()),
             optional: true,
         });
 
@@ -88,7 +88,7 @@ impl FilterRasterFeaturesByArea {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=input.tif -o=output.tif --background=zero",
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=input.tif -o=output.tif --threshold=100 --background=zero",
             short_exe, name
         )
         .replace("*", &sep);
@@ -136,64 +136,21 @@ impl WhiteboxTool for FilterRasterFeaturesByArea {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut threshold = 0usize;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let threshold = parsed.get_usize("threshold")?.unwrap_or(0usize);
         let mut background_value = String::new();
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-threshold" {
-                threshold = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-background" {
-                let background_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                background_value = if background_str.contains("z") {
-                    "zero".to_string()
-                } else {
-                    "nodata".to_string()
-                };
-            }
+        if let Some(value) = parsed.get_string("background") {
+            let background_str = value.to_string();
+            background_value = if background_str.contains("z") {
+                "zero".to_string()
+            } else {
+                "nodata".to_string()
+            };
         }
 
         if verbose {
@@ -207,17 +164,11 @@ impl WhiteboxTool for FilterRasterFeaturesByArea {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -246,16 +197,17 @@ impl WhiteboxTool for FilterRasterFeaturesByArea {
         for row in 0..rows {
             for col in 0..columns {
                 value = input.get_value(row, col);
-                if value != nodata {
+                if !is_nodata(value, nodata) {
                     bin = (value - min_val) as usize;
                     histo[bin] += 1;
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -268,7 +220,7 @@ impl WhiteboxTool for FilterRasterFeaturesByArea {
                 .par_iter()
                 .map(|value| {
                     let mut ret_value = nodata;
-                    if *value != nodata {
+                    if !is_nodata(*value, nodata) {
                         let bin = (value - min_val) as usize;
                         if histo[bin] >= threshold {
                             ret_value = *value;
@@ -282,10 +234,11 @@ impl WhiteboxTool for FilterRasterFeaturesByArea {
 
             output.set_row_data(row, new_vals);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use crate::tools::*;
 use whitebox_vector::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool locates the lowest and/or highest cells in a raster and outputs these locations to a vector
 /// points file. The user must specify the name of the input raster (`--input`) and the name of the output
@@ -140,54 +139,23 @@ impl WhiteboxTool for FindLowestOrHighestPoints {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut out_type = "lowest".to_owned();
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_string("out_type") {
+            out_type = value.to_lowercase();
+            if out_type.contains("low") {
+                out_type = "lowest".to_owned();
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_type" {
-                out_type = if keyval {
-                    vec[1].to_lowercase()
-                } else {
-                    args[i + 1].to_lowercase()
-                };
-                if out_type.contains("low") {
-                    out_type = "lowest".to_owned();
-                }
-                if out_type.contains("hi") {
-                    out_type = "highest".to_owned();
-                }
-                if out_type.contains("b") {
-                    out_type = "both".to_owned();
-                }
+            if out_type.contains("hi") {
+                out_type = "highest".to_owned();
+            }
+            if out_type.contains("b") {
+                out_type = "both".to_owned();
             }
         }
 
@@ -205,14 +173,8 @@ impl WhiteboxTool for FindLowestOrHighestPoints {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -236,19 +198,17 @@ impl WhiteboxTool for FindLowestOrHighestPoints {
         // loop through the raster, locating the min/max
         let rows_completed = Arc::new(Mutex::new(0..rows));
         let old_progress = Arc::new(Mutex::new(1));
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let input = input.clone();
             let rows_completed = rows_completed.clone();
             let old_progress = old_progress.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut z: f64;
                 let mut low_z = f64::INFINITY;
                 let mut low_row = 0isize;
@@ -259,9 +219,12 @@ impl WhiteboxTool for FindLowestOrHighestPoints {
                 let mut progress: usize;
                 // let mut old_progress: usize = 1;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             if z < low_z {
                                 low_z = z;
                                 low_col = col;
@@ -282,13 +245,17 @@ impl WhiteboxTool for FindLowestOrHighestPoints {
                         progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                         let mut p = old_progress.lock().unwrap();
                         if progress != *p {
-                            println!("Progress: {}%", progress);
+                            if tx.send(WorkerMessage::Progress("Progress", progress)).is_err() {
+                                return;
+                            }
                             *p = progress;
                         }
                     }
                 }
-                tx.send((low_z, low_col, low_row, high_z, high_col, high_row))
-                    .unwrap();
+                let data = (low_z, low_col, low_row, high_z, high_col, high_row);
+                if tx.send(WorkerMessage::Data(data)).is_err() {
+                    return;
+                }
             });
         }
 
@@ -299,7 +266,7 @@ impl WhiteboxTool for FindLowestOrHighestPoints {
         let mut high_row = 0isize;
         let mut high_col = 0isize;
         for _ in 0..num_procs {
-            let data = rx.recv().expect("Error receiving data from thread.");
+            let data = recv_data(&rx, reporter).expect("Error receiving data from thread.");
             if data.0 < low_z {
                 low_z = data.0;
                 low_col = data.1;
@@ -311,6 +278,7 @@ impl WhiteboxTool for FindLowestOrHighestPoints {
                 high_row = data.5;
             }
         }
+        reporter.check_cancelled()?;
 
         // add the vector record(s)
         let mut rec_num = 1i32;
@@ -21,14 +21,13 @@ use whitebox_raster::*;
 use whitebox_common::structures::{DistanceMetric, FixedRadiusSearch2D};
 use crate::tools::*;
 use whitebox_vector::{FieldData, ShapeType, Shapefile};
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool interpolates vector points into a raster surface using an inverse-distance weighted scheme.
 pub struct IdwInterpolation {
@@ -67,7 +66,7 @@ impl IdwInterpolation {
                 "--input".to_string(),
             ),
             default_value: None,
-            optional: false,
+            optional: true,
         });
 
         parameters.push(ToolParameter {
@@ -117,7 +116,7 @@ impl IdwInterpolation {
 
         parameters.push(ToolParameter{
             name: "Cell Size (optional)".to_owned(), 
-            flags: vec!["--cell_size".to_owned()], 
+            flags: vec!["--resolution".to_owned(), "--cell_size".to_owned()], 
             description: "Optionally specified cell size of output raster. Not used when base raster is specified.".to_owned(),
             parameter_type: ParameterType::Float,
             default_value: None,
@@ -192,119 +191,20 @@ impl WhiteboxTool for IdwInterpolation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut field_name = String::new();
-        let mut use_z = false;
-        let mut output_file = String::new();
-        let mut grid_res = 0f64;
-        let mut base_file = String::new();
-        let mut weight = 2f64;
-        let mut radius = 0f64;
-        let mut min_points = 0usize;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let field_name = parsed.get_string("field").unwrap_or_default();
+        let use_z = parsed.get_bool("use_z");
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let grid_res = parsed.get_f64("cell_size").unwrap_or(0f64);
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+        let weight = parsed.get_f64("weight").unwrap_or(2f64);
+        let radius = parsed.get_f64("radius").unwrap_or(0f64);
+        let min_points = parsed.get_usize("min_points")?.unwrap_or(0usize);
         // let mut max_dist = f64::INFINITY;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                field_name = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-use_z" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    use_z = true;
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" || flag_val == "-cell_size" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-base" {
-                base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-weight" {
-                weight = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-radius" {
-                radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-min_points" {
-                min_points = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-                // } else if flag_val == "-max_dist" {
-                //     max_dist = if keyval {
-                //         vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                //     } else {
-                //         args[i+1].to_string().parse::<f64>().unwrap()
-                //     };
-            }
-        }
-
         if verbose {
             let tool_name = self.get_tool_name();
             let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28); 
@@ -316,17 +216,11 @@ impl WhiteboxTool for IdwInterpolation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         // radius = radius * radius; // squared distances are used
 
@@ -488,12 +382,13 @@ impl WhiteboxTool for IdwInterpolation {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * record_num as f64
                         / (vector_data.num_records - 1) as f64)
                         as usize;
                     if progress != old_progress {
-                        println!("Creating search structure: {}%", progress);
+                        reporter.progress("Creating search structure", progress);
                         old_progress = progress;
                     }
                 }
@@ -525,12 +420,13 @@ impl WhiteboxTool for IdwInterpolation {
                     // p += 1;
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * record_num as f64
                         / (vector_data.num_records - 1) as f64)
                         as usize;
                     if progress != old_progress {
-                        println!("Creating search structure: {}%", progress);
+                        reporter.progress("Creating search structure", progress);
                         old_progress = progress;
                     }
                 }
@@ -546,9 +442,7 @@ impl WhiteboxTool for IdwInterpolation {
         let nodata = -32768.0f64;
 
         let mut output = if !base_file.trim().is_empty() || grid_res == 0f64 {
-            if !base_file.contains(&sep) && !base_file.contains("/") {
-                base_file = format!("{}{}", working_directory, base_file);
-            }
+            base_file = resolve_input_path(&base_file, working_directory)?;
             let mut base = Raster::new(&base_file, "r")?;
             base.configs.nodata = nodata;
             Raster::initialize_using_file(&output_file, &base)
@@ -596,18 +490,14 @@ impl WhiteboxTool for IdwInterpolation {
 
         // let kdtree = Arc::new(kdtree); // wrap FRS in an Arc
         let frs = Arc::new(frs);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             // let kdtree = kdtree.clone();
             let frs = frs.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut x, mut y): (f64, f64);
                 let mut zn: f64;
                 let mut dist: f64;
@@ -643,7 +533,9 @@ impl WhiteboxTool for IdwInterpolation {
                             }
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
                 // if radius > 0f64 {
                 //     for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -715,10 +607,11 @@ impl WhiteboxTool for IdwInterpolation {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -175,66 +175,22 @@ impl WhiteboxTool for Intersect {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut overlay_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut overlay_file = parsed.get_string("overlay").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut precision = std::f64::EPSILON;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val.contains("-input") {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-overlay" {
-                overlay_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-snap" {
-                precision = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if precision == 0f64 {
-                    precision = std::f64::EPSILON;
-                }
+        if let Some(value) = parsed.get_f64("snap") {
+            precision = value;
+            if precision == 0f64 {
+                precision = std::f64::EPSILON;
             }
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -251,15 +207,9 @@ impl WhiteboxTool for Intersect {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !overlay_file.contains(&sep) && !overlay_file.contains("/") {
-            overlay_file = format!("{}{}", working_directory, overlay_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        overlay_file = resolve_input_path(&overlay_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -324,11 +274,13 @@ impl WhiteboxTool for Intersect {
                 let mut tree = KdTree::with_capacity(dimensions, capacity_per_node);
                 let mut p: Point2D;
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     p = record.points[0];
                     tree.add([p.x, p.y], (1, record_num)).unwrap();
                 }
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     p = record.points[0];
                     tree.add([p.x, p.y], (2, record_num)).unwrap();
@@ -374,10 +326,11 @@ impl WhiteboxTool for Intersect {
                         }
                         output.attributes.add_record(out_atts, false);
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * record_num as f64 / num_total_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -391,6 +344,7 @@ impl WhiteboxTool for Intersect {
                 let mut p: Point2D;
                 let mut total_points = 0;
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for p in &record.points {
                         tree.add([p.x, p.y], 1).unwrap();
@@ -398,6 +352,7 @@ impl WhiteboxTool for Intersect {
                     }
                 }
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     for p in &record.points {
                         tree.add([p.x, p.y], 2).unwrap();
@@ -423,10 +378,11 @@ impl WhiteboxTool for Intersect {
                                 break;
                             }
                         }
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * i as f64 / (total_points - 1) as f64) as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -446,6 +402,7 @@ impl WhiteboxTool for Intersect {
                     match input.header.shape_type.dimension() {
                         ShapeTypeDimension::XY => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i] {
@@ -456,6 +413,7 @@ impl WhiteboxTool for Intersect {
                         }
                         ShapeTypeDimension::Measure => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i] {
@@ -469,6 +427,7 @@ impl WhiteboxTool for Intersect {
                         }
                         ShapeTypeDimension::Z => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i] {
@@ -497,6 +456,7 @@ impl WhiteboxTool for Intersect {
                 let mut last_point_in_part: usize;
                 let mut polylines: Vec<Polyline> = vec![];
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -517,6 +477,7 @@ impl WhiteboxTool for Intersect {
                 }
 
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -577,10 +538,11 @@ impl WhiteboxTool for Intersect {
 
                     num_neighbours.push(line_num_neighbours);
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -610,10 +572,11 @@ impl WhiteboxTool for Intersect {
                     }
                     features_polylines.push(pl.clone());
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -623,6 +586,7 @@ impl WhiteboxTool for Intersect {
                 let mut duplicate = vec![false; features_polylines.len()];
                 let mut duplicate_partner = vec![0; features_polylines.len()];
                 for i in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     if !duplicate[i] {
                         for j in (i + 1)..features_polylines.len() {
                             if features_polylines[i] == features_polylines[j] {
@@ -641,6 +605,7 @@ impl WhiteboxTool for Intersect {
 
                 let mut fid = 1i32;
                 for i in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     if duplicate[i] && features_polylines[i].source_file == 1 {
                         let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
                         sfg.add_part(&features_polylines[i].vertices);
@@ -681,6 +646,7 @@ impl WhiteboxTool for Intersect {
 
                 // Read in the features
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     let mut mpl = MultiPolyline::new(record_num);
                     let mut holes = vec![false; record.num_parts as usize];
@@ -709,6 +675,7 @@ impl WhiteboxTool for Intersect {
                 }
 
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     let mut mpl = MultiPolyline::new(record_num);
                     let mut holes = vec![false; record.num_parts as usize];
@@ -1764,12 +1731,13 @@ impl WhiteboxTool for Intersect {
                             }
                         }
 
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (record_num + 1) as f64
                                 / multipolylines.len() as f64)
                                 as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
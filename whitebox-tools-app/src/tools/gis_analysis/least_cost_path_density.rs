@@ -0,0 +1,649 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use crate::tools::*;
+use whitebox_vector::{ShapeType, Shapefile};
+use rand::rngs::StdRng;
+use rand::seq::index;
+use rand::SeedableRng;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool maps corridor use, i.e. the density of least-cost paths, between two sets of point
+/// locations over a cost (friction) surface. For each pair of an origin (`--origins`) and a
+/// destination (`--destinations`), the tool finds the least-cost path from the origin to the
+/// destination over the cost raster (`--cost`), and each cell of the output raster (`--output`)
+/// counts the number of paths that cross it. Cells that are crossed by many paths are important to
+/// the connectivity between the two sets of locations, e.g. habitat patches, and are candidates for
+/// conservation or restoration. If no destinations are specified, the paths connect each pair of
+/// the origins, with one path per pair.
+///
+/// As in the `CostDistance` tool, the cost of travelling between two neighbouring cells is the mean
+/// of their costs multiplied by the distance between their centres, paths may move to any of the
+/// eight neighbours of a cell, and NoData cells of the cost raster are impassable. Points that are
+/// beyond the grid or on NoData cells are ignored, and pairs that cannot be connected are reported.
+///
+/// The number of paths grows with the product of the numbers of origins and destinations. The
+/// `--max_pairs` parameter limits the analysis to a random sample of that many pairs, which is
+/// drawn reproducibly for a given `--seed`. With the `--normalize` flag, the output is the
+/// proportion of the connected pairs whose paths cross each cell, rather than their number, which
+/// makes the densities of samples of different sizes comparable.
+///
+/// The least-cost paths from each origin are found with a single run of Dijkstra's algorithm, which
+/// stops once all of the origin's destinations are reached, and the origins are processed in
+/// parallel.
+///
+/// # See Also
+/// `CostDistance`, `CostPathway`, `CostAllocation`
+pub struct LeastCostPathDensity {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LeastCostPathDensity {
+    pub fn new() -> LeastCostPathDensity {
+        // public constructor
+        let name = "LeastCostPathDensity".to_string();
+        let toolbox = "GIS Analysis/Distance Tools".to_string();
+        let description =
+            "Counts the least-cost paths between pairs of origin and destination points that cross each cell (corridor use)."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Origin Points File".to_owned(),
+            flags: vec!["--origins".to_owned()],
+            description: "Input vector points file of the origins.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Destination Points File (optional)".to_owned(),
+            flags: vec!["--destinations".to_owned()],
+            description: "Input vector points file of the destinations; if unspecified, each pair of origins is connected.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Cost (Friction) File".to_owned(),
+            flags: vec!["--cost".to_owned()],
+            description: "Input cost (friction) raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output path density raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Number of Pairs (optional)".to_owned(),
+            flags: vec!["--max_pairs".to_owned()],
+            description: "Number of randomly sampled pairs to connect; if unspecified, all pairs are connected.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Random Seed".to_owned(),
+            flags: vec!["--seed".to_owned()],
+            description: "Seed of the random sample of pairs.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("42".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Normalize by the number of pairs?".to_owned(),
+            flags: vec!["--normalize".to_owned()],
+            description: "Output the proportion of connected pairs whose paths cross each cell.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --origins=patches.shp --destinations=reserves.shp --cost=resistance.tif -o=corridors.tif
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --origins=patches.shp --cost=resistance.tif -o=corridors.tif --max_pairs=10000 --seed=7 --normalize", short_exe, name).replace("*", &sep);
+
+        LeastCostPathDensity {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LeastCostPathDensity {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let origins_file = parsed.get_string("origins").unwrap_or_default();
+        let destinations_file = parsed.get_string("destinations").unwrap_or_default();
+        let cost_file = parsed.get_string("cost").unwrap_or_default();
+        let output_file = parsed.get_string("output").unwrap_or_default();
+        let max_pairs = parsed.get_usize("max_pairs")?;
+        let seed = parsed.get_i64("seed").unwrap_or(42) as u64;
+        let normalize = parsed.get_bool("normalize");
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if max_pairs == Some(0) {
+            return Err(whitebox_common::errors::ErrorCause::InvalidParameter
+                .error("The --max_pairs parameter must be greater than zero."));
+        }
+
+        let origins_file = resolve_input_path(&origins_file, working_directory)?;
+        let destinations_file = if destinations_file.trim().is_empty() {
+            None
+        } else {
+            Some(resolve_input_path(&destinations_file, working_directory)?)
+        };
+        let cost_file = resolve_input_path(&cost_file, working_directory)?;
+        let output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let cost = Raster::new(&cost_file, "r")?;
+        let origins = point_cells(&Shapefile::read(&origins_file)?, &cost, "origins", verbose)?;
+        let destinations = match &destinations_file {
+            Some(f) => Some(point_cells(&Shapefile::read(f)?, &cost, "destinations", verbose)?),
+            None => None,
+        };
+
+        let start = Instant::now();
+
+        // The pairs are numbered, such that a random sample of them can be drawn without listing
+        // them all, and then grouped by origin.
+        let num_origins = origins.len();
+        let num_pairs = match &destinations {
+            Some(d) => num_origins * d.len(),
+            None => num_origins * num_origins.saturating_sub(1) / 2,
+        };
+        if num_pairs == 0 {
+            return Err(whitebox_common::errors::ErrorCause::InvalidParameter
+                .error("There are no pairs of points on valid cells of the cost raster to connect."));
+        }
+        let pair = |k: usize| -> (usize, usize) {
+            match &destinations {
+                Some(d) => (k / d.len(), d[k % d.len()]),
+                None => {
+                    let (i, j) = unordered_pair(k, num_origins);
+                    (i, origins[j])
+                }
+            }
+        };
+        let mut targets: Vec<Vec<usize>> = vec![vec![]; num_origins];
+        let num_sampled = match max_pairs {
+            Some(m) if m < num_pairs => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                for k in index::sample(&mut rng, num_pairs, m).into_iter() {
+                    let (i, target) = pair(k);
+                    targets[i].push(target);
+                }
+                m
+            }
+            _ => {
+                for k in 0..num_pairs {
+                    let (i, target) = pair(k);
+                    targets[i].push(target);
+                }
+                num_pairs
+            }
+        };
+        if verbose {
+            println!("Connecting {} pairs of points...", num_sampled);
+        }
+
+        let jobs: Vec<(usize, Vec<usize>)> = targets
+            .into_iter()
+            .enumerate()
+            .filter(|(_, t)| !t.is_empty())
+            .map(|(i, t)| (origins[i], t))
+            .collect();
+        let num_jobs = jobs.len();
+        let jobs = Arc::new(jobs);
+        let grid = Arc::new(CostGrid::new(&cost));
+        let stop = Arc::new(AtomicBool::new(false));
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings()
+            .max_threads(num_jobs)
+            .num_threads();
+        let (tx, rx) = mpsc::channel();
+        let mut handles = Vec::with_capacity(num_procs);
+        for tid in 0..num_procs {
+            let jobs = jobs.clone();
+            let grid = grid.clone();
+            let stop = stop.clone();
+            let tx = tx.clone();
+            handles.push(spawn(move || {
+                let mut search = PathSearch::new(grid.rows * grid.columns);
+                let mut counts = vec![0u32; grid.rows * grid.columns];
+                for (origin, targets) in jobs.iter().skip(tid).step_by(num_procs) {
+                    if stop.load(AtomicOrdering::Relaxed) {
+                        break;
+                    }
+                    let connected = search.route(&grid, *origin, targets, &mut counts);
+                    if tx.send((connected, targets.len() - connected)).is_err() {
+                        break;
+                    }
+                }
+                counts
+            }));
+        }
+        drop(tx);
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        let (mut num_connected, mut num_unconnected) = (0usize, 0usize);
+        for j in 0..num_jobs {
+            let (connected, unconnected) = rx.recv().expect("Error receiving data from thread.");
+            num_connected += connected;
+            num_unconnected += unconnected;
+            if let Err(e) = reporter.check_cancelled() {
+                stop.store(true, AtomicOrdering::Relaxed);
+                return Err(e);
+            }
+            if verbose {
+                progress = (100.0_f64 * j as f64 / (num_jobs - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Tracing paths", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        let mut counts = vec![0u32; grid.rows * grid.columns];
+        for handle in handles {
+            let c = handle.join().expect("Error joining a path-tracing thread.");
+            for (total, n) in counts.iter_mut().zip(c) {
+                *total += n;
+            }
+        }
+        if verbose && num_unconnected > 0 {
+            println!(
+                "Warning: {} of the {} pairs could not be connected, because NoData cells of the cost raster separate them.",
+                num_unconnected, num_sampled
+            );
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &cost);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "spectrum.plt".to_string();
+        let out_nodata = output.configs.nodata;
+        let scale = if normalize && num_connected > 0 {
+            1.0 / num_connected as f64
+        } else {
+            1.0
+        };
+        for row in 0..grid.rows {
+            reporter.check_cancelled()?;
+            let data = (0..grid.columns)
+                .map(|col| {
+                    let i = row * grid.columns + col;
+                    if grid.costs[i].is_some() {
+                        counts[i] as f64 * scale
+                    } else {
+                        out_nodata
+                    }
+                })
+                .collect();
+            output.set_row_data(row as isize, data);
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Origins file: {}", origins_file));
+        if let Some(f) = &destinations_file {
+            output.add_metadata_entry(format!("Destinations file: {}", f));
+        }
+        output.add_metadata_entry(format!("Cost raster: {}", cost_file));
+        output.add_metadata_entry(format!("Connected pairs: {}", num_connected));
+        if num_sampled < num_pairs {
+            output.add_metadata_entry(format!("Sampled pairs: {} of {} (seed {})", num_sampled, num_pairs, seed));
+        }
+        output.add_metadata_entry(format!("Normalized: {}", normalize));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the indices of the valid cells of the cost raster that contain the points of `points`.
+fn point_cells(points: &Shapefile, cost: &Raster, label: &str, verbose: bool) -> Result<Vec<usize>, Error> {
+    if points.header.shape_type.base_shape_type() != ShapeType::Point
+        && points.header.shape_type.base_shape_type() != ShapeType::MultiPoint
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("The input {} must be of point base shape type.", label),
+        ));
+    }
+    let rows = cost.configs.rows as isize;
+    let columns = cost.configs.columns as isize;
+    let nodata = cost.configs.nodata;
+    let mut cells = vec![];
+    let mut num_ignored = 0;
+    for record_num in 0..points.num_records {
+        for p in &points.get_record(record_num).points {
+            let row = cost.get_row_from_y(p.y);
+            let col = cost.get_column_from_x(p.x);
+            if row >= 0 && row < rows && col >= 0 && col < columns && !is_nodata(cost.get_value(row, col), nodata) {
+                cells.push((row * columns + col) as usize);
+            } else {
+                num_ignored += 1;
+            }
+        }
+    }
+    if verbose && num_ignored > 0 {
+        println!(
+            "Warning: {} of the {} are beyond the cost raster or on NoData cells, and are ignored.",
+            num_ignored, label
+        );
+    }
+    Ok(cells)
+}
+
+/// Returns the `k`th of the unordered pairs (i, j), with i < j, of `n` items, in row-major order.
+fn unordered_pair(k: usize, n: usize) -> (usize, usize) {
+    // row i starts at the pair numbered i * (2n - i - 1) / 2
+    let start = |i: usize| i * (2 * n - i - 1) / 2;
+    let nf = n as f64;
+    let mut i = ((2.0 * nf - 1.0 - ((2.0 * nf - 1.0).powi(2) - 8.0 * k as f64).max(0.0).sqrt()) / 2.0) as usize;
+    // correct any rounding of the estimate
+    while i > 0 && start(i) > k {
+        i -= 1;
+    }
+    while i + 1 < n && start(i + 1) <= k {
+        i += 1;
+    }
+    (i, i + 1 + k - start(i))
+}
+
+/// The costs of the cells of a cost raster, in row-major order, with `None` for NoData cells.
+struct CostGrid {
+    rows: usize,
+    columns: usize,
+    costs: Vec<Option<f64>>,
+    /// The distances to the neighbours in the directions of `DX` and `DY`.
+    distances: [f64; 8],
+}
+
+const DX: [isize; 8] = [1, 1, 1, 0, -1, -1, -1, 0];
+const DY: [isize; 8] = [-1, 0, 1, 1, 1, 0, -1, -1];
+
+impl CostGrid {
+    fn new(cost: &Raster) -> CostGrid {
+        let rows = cost.configs.rows;
+        let columns = cost.configs.columns;
+        let nodata = cost.configs.nodata;
+        let mut costs = Vec::with_capacity(rows * columns);
+        for row in 0..rows as isize {
+            for col in 0..columns as isize {
+                let z = cost.get_value(row, col);
+                costs.push(if !is_nodata(z, nodata) { Some(z) } else { None });
+            }
+        }
+        let (res_x, res_y) = (cost.configs.resolution_x, cost.configs.resolution_y);
+        let diagonal = (res_x * res_x + res_y * res_y).sqrt();
+        CostGrid {
+            rows: rows,
+            columns: columns,
+            costs: costs,
+            distances: [diagonal, res_x, diagonal, res_y, diagonal, res_x, diagonal, res_y],
+        }
+    }
+}
+
+/// The state of Dijkstra's algorithm for one origin at a time. Only the cells that a search
+/// touches are reset before the next one.
+struct PathSearch {
+    accumulated: Vec<f64>,
+    /// The direction (index of `DX` and `DY`) of the step into each reached cell, or `ORIGIN`.
+    direction: Vec<u8>,
+    settled: Vec<bool>,
+    is_target: Vec<bool>,
+    touched: Vec<usize>,
+}
+
+const UNREACHED: u8 = u8::MAX;
+const ORIGIN: u8 = u8::MAX - 1;
+
+impl PathSearch {
+    fn new(num_cells: usize) -> PathSearch {
+        PathSearch {
+            accumulated: vec![f64::INFINITY; num_cells],
+            direction: vec![UNREACHED; num_cells],
+            settled: vec![false; num_cells],
+            is_target: vec![false; num_cells],
+            touched: vec![],
+        }
+    }
+
+    /// Finds the least-cost paths from `origin` to each of `targets`, adding one to the counts of
+    /// the cells along each path, and returns the number of targets that were reached.
+    fn route(&mut self, grid: &CostGrid, origin: usize, targets: &[usize], counts: &mut [u32]) -> usize {
+        let mut remaining = 0;
+        for t in targets {
+            if !self.is_target[*t] {
+                self.is_target[*t] = true;
+                remaining += 1;
+            }
+        }
+        let columns = grid.columns as isize;
+        let rows = grid.rows as isize;
+        let mut heap = BinaryHeap::new();
+        self.accumulated[origin] = 0.0;
+        self.direction[origin] = ORIGIN;
+        self.touched.push(origin);
+        heap.push(Node { cost: 0.0, index: origin });
+        while let Some(Node { cost, index }) = heap.pop() {
+            if self.settled[index] {
+                continue;
+            }
+            self.settled[index] = true;
+            if self.is_target[index] {
+                remaining -= 1;
+                if remaining == 0 {
+                    break;
+                }
+            }
+            let (row, col) = (index as isize / columns, index as isize % columns);
+            let cost1 = grid.costs[index].unwrap_or(0.0);
+            for n in 0..8 {
+                let (row_n, col_n) = (row + DY[n], col + DX[n]);
+                if row_n < 0 || row_n >= rows || col_n < 0 || col_n >= columns {
+                    continue;
+                }
+                let index_n = (row_n * columns + col_n) as usize;
+                if self.settled[index_n] {
+                    continue;
+                }
+                if let Some(cost2) = grid.costs[index_n] {
+                    let new_cost = cost + (cost1 + cost2) / 2.0 * grid.distances[n];
+                    if new_cost < self.accumulated[index_n] {
+                        if self.direction[index_n] == UNREACHED {
+                            self.touched.push(index_n);
+                        }
+                        self.accumulated[index_n] = new_cost;
+                        self.direction[index_n] = n as u8;
+                        heap.push(Node { cost: new_cost, index: index_n });
+                    }
+                }
+            }
+        }
+
+        // trace each target back to the origin
+        let mut num_reached = 0;
+        for t in targets {
+            if !self.settled[*t] {
+                continue;
+            }
+            num_reached += 1;
+            let mut index = *t;
+            loop {
+                counts[index] += 1;
+                let n = self.direction[index];
+                if n == ORIGIN {
+                    break;
+                }
+                let n = n as usize;
+                let (row, col) = (index as isize / columns - DY[n], index as isize % columns - DX[n]);
+                index = (row * columns + col) as usize;
+            }
+        }
+
+        for i in self.touched.drain(..) {
+            self.accumulated[i] = f64::INFINITY;
+            self.direction[i] = UNREACHED;
+            self.settled[i] = false;
+        }
+        for t in targets {
+            self.is_target[*t] = false;
+        }
+        num_reached
+    }
+}
+
+/// A cell in the priority queue of a search, ordered such that the least accumulated cost is
+/// popped first from the max-heap.
+struct Node {
+    cost: f64,
+    index: usize,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
@@ -10,13 +10,12 @@ use whitebox_common::algorithms::find_line_intersections;
 use whitebox_common::structures::{BoundingBox, Point2D};
 use crate::tools::*;
 use whitebox_vector::*;
-use num_cpus;
 use std::env;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool identifies points where the features of two vector line/polygon layers
 /// intersect. The user must specify the names of two input vector line files and the
@@ -148,50 +147,15 @@ impl WhiteboxTool for LineIntersections {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input1_file: String = "".to_string();
-        let mut input2_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input1_file: String = parsed.get_string("input1").unwrap_or_default();
+        let mut input2_file: String = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i1" || flag_val == "-input1" {
-                input1_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-i2" || flag_val == "-input2" {
-                input2_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -208,17 +172,11 @@ impl WhiteboxTool for LineIntersections {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input1_file.contains(path::MAIN_SEPARATOR) && !input1_file.contains("/") {
-            input1_file = format!("{}{}", working_directory, input1_file);
-        }
+        input1_file = resolve_input_path(&input1_file, working_directory)?;
 
-        if !input2_file.contains(path::MAIN_SEPARATOR) && !input2_file.contains("/") {
-            input2_file = format!("{}{}", working_directory, input2_file);
-        }
+        input2_file = resolve_input_path(&input2_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input1 = Arc::new(Shapefile::read(&input1_file)?);
 
@@ -280,12 +238,8 @@ impl WhiteboxTool for LineIntersections {
         }
 
         // multithreading setup
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx1, rx1) = mpsc::channel();
         let (tx2, rx2) = mpsc::channel();
         let feature_list = Arc::new(Mutex::new(0..input1.num_records));
@@ -293,6 +247,7 @@ impl WhiteboxTool for LineIntersections {
         let bb2 = Arc::new(bb2);
 
         // hunt for intersections in the overlapping bounding boxes
+        let token = reporter.cancellation_token();
         for _ in 0..num_procs {
             let input1 = input1.clone();
             let input2 = input2.clone();
@@ -301,7 +256,8 @@ impl WhiteboxTool for LineIntersections {
             let feature_list = feature_list.clone();
             let tx1 = tx1.clone();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut start_point_in_part1: usize;
                 let mut start_point_in_part2: usize;
                 let mut end_point_in_part1: usize;
@@ -310,6 +266,9 @@ impl WhiteboxTool for LineIntersections {
                 let mut record_num1 = 0;
                 let mut intersection_points: Vec<(Point2D, usize, usize)> = vec![];
                 while record_num1 < input1.num_records {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     let mut num_intersections = 0;
                     // Get the next tile up for interpolation
                     record_num1 = match feature_list.lock().unwrap().next() {
@@ -363,10 +322,13 @@ impl WhiteboxTool for LineIntersections {
                             }
                         }
                     }
-                    tx2.send(num_intersections).unwrap();
+                    if tx2.send(num_intersections).is_err() {
+                        return;
+                    }
+                }
+                if tx1.send((intersection_points.clone(), print_warning)).is_err() {
+                    return;
                 }
-                tx1.send((intersection_points.clone(), print_warning))
-                    .unwrap();
             });
         }
 
@@ -434,18 +396,17 @@ impl WhiteboxTool for LineIntersections {
                 fid += 1;
             }
         }
+        reporter.check_cancelled()?;
 
         let mut num_intersections = 0;
         for i in 0..input1.num_records {
             let found_intersections = rx2.recv().unwrap();
             num_intersections += found_intersections;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / input1.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!(
-                        "Progress ({} intersections found): {}%",
-                        num_intersections, progress
-                    );
+                    reporter.progress(&format!("Progress ({} intersections found)", num_intersections), progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,588 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{
+    cumulative_distances, extract_measure_range, locate_measure, offset_point,
+};
+use whitebox_common::structures::Point2D;
+use crate::tools::*;
+use whitebox_vector::*;
+use std::collections::HashMap;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufReader, Error, ErrorKind};
+use std::path;
+
+/// This tool locates events stored in a table along a set of measured routes, a process known as
+/// dynamic segmentation in linear referencing. The events table (`--events`) is a comma-separated
+/// values (CSV) file with a header row, in which each record identifies the route on which an event
+/// is located (`--event_route_field`) and its position along that route. The routes (`--routes`)
+/// are vector polylines with an identifying field (`--route_field`), whose values are matched to the
+/// event route identifiers. Route measures are taken from the M-values of the route vertices (see
+/// `CalibrateRoutes`) or, if the routes do not contain measures, from the distance along each route.
+///
+/// Two types of events are supported:
+///
+/// - **Point events** (e.g. accident locations or water quality samples) have a single measure
+///   (`--measure_field`) and are output as vector points. An optional `--offset_field` may be used to
+///   displace points perpendicular to the route; positive offsets are located to the right of the
+///   route direction and negative offsets to the left.
+///
+/// - **Line events** (e.g. pavement condition or habitat reaches) have from- and to-measures
+///   (`--from_field` and `--to_field`) and are output as the portions of the routes between these
+///   measures. Events extending beyond the ends of a route are clipped to the route.
+///
+/// All of the fields in the events table are copied to the output attribute table. Events that
+/// reference a route that cannot be found, or with measures that lie outside the range of the route
+/// measures, are not output and are reported as location errors.
+///
+/// # See Also
+/// `CalibrateRoutes`, `LinesToPoints`
+pub struct LocateRouteEvents {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LocateRouteEvents {
+    pub fn new() -> LocateRouteEvents {
+        // public constructor
+        let name = "LocateRouteEvents".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Locates point and line events from a table along measured routes (dynamic segmentation)."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Routes File".to_owned(),
+            flags: vec!["--routes".to_owned()],
+            description: "Input vector polyline routes file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Route ID Field".to_owned(),
+            flags: vec!["--route_field".to_owned()],
+            description: "Field in the routes attribute table identifying each route.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--routes".to_string(),
+            ),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Events Table (CSV)".to_owned(),
+            flags: vec!["--events".to_owned()],
+            description: "Input events table file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Event Route ID Field".to_owned(),
+            flags: vec!["--event_route_field".to_owned()],
+            description: "Name of the events table field containing route identifiers.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Measure Field (point events)".to_owned(),
+            flags: vec!["--measure_field".to_owned()],
+            description: "Name of the events table field containing point event measures (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "From-Measure Field (line events)".to_owned(),
+            flags: vec!["--from_field".to_owned()],
+            description: "Name of the events table field containing line event from-measures (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "To-Measure Field (line events)".to_owned(),
+            flags: vec!["--to_field".to_owned()],
+            description: "Name of the events table field containing line event to-measures (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Offset Field (point events)".to_owned(),
+            flags: vec!["--offset_field".to_owned()],
+            description: "Name of the events table field containing lateral offsets of point events (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --routes=routes.shp --route_field=ROUTE_ID --events=accidents.csv --event_route_field=ROAD --measure_field=KM -o=accidents.shp
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --routes=routes.shp --route_field=ROUTE_ID --events=pavement.csv --event_route_field=ROAD --from_field=FROM_KM --to_field=TO_KM -o=pavement.shp",
+            short_exe, name
+        ).replace("*", &sep);
+
+        LocateRouteEvents {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LocateRouteEvents {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut routes_file = parsed.get_string("routes").unwrap_or_default();
+        let route_field = parsed.get_string("route_field").unwrap_or_default();
+        let mut events_file = parsed.get_string("events").unwrap_or_default();
+        let event_route_field = parsed.get_string("event_route_field").unwrap_or_default();
+        let measure_field = parsed.get_string("measure_field").unwrap_or_default();
+        let from_field = parsed.get_string("from_field").unwrap_or_default();
+        let to_field = parsed.get_string("to_field").unwrap_or_default();
+        let offset_field = parsed.get_string("offset_field").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+
+        // read the arguments
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        routes_file = resolve_input_path(&routes_file, working_directory)?;
+        events_file = resolve_path(&events_file, working_directory);
+        output_file = resolve_path(&output_file, working_directory);
+
+        let line_events = !from_field.is_empty() || !to_field.is_empty();
+        if line_events && (from_field.is_empty() || to_field.is_empty()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Line events require both a from-measure (--from_field) and to-measure (--to_field) field.",
+            ));
+        }
+        if !line_events && measure_field.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either a measure field (--measure_field) or from- and to-measure fields (--from_field, --to_field) must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let routes = Shapefile::read(&routes_file)?;
+        if routes.header.shape_type.base_shape_type() != ShapeType::PolyLine {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input routes file must be of a POLYLINE base shape type.",
+            ));
+        }
+        if routes.attributes.get_field_num(&route_field).is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The route ID field '{}' does not exist in the routes attribute table.", route_field),
+            ));
+        }
+
+        let (headers, rows) = read_csv_table(&events_file)?;
+        let find_column = |name: &str| -> Result<usize, Error> {
+            match headers.iter().position(|h| h.eq_ignore_ascii_case(name.trim())) {
+                Some(i) => Ok(i),
+                None => Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The field '{}' does not exist in the events table.", name),
+                )),
+            }
+        };
+        let route_col = find_column(&event_route_field)?;
+        let (measure_col, from_col, to_col) = if line_events {
+            (0, find_column(&from_field)?, find_column(&to_field)?)
+        } else {
+            (find_column(&measure_field)?, 0, 0)
+        };
+        let offset_col = if !line_events && !offset_field.is_empty() {
+            Some(find_column(&offset_field)?)
+        } else {
+            None
+        };
+
+        // Build the measured parts of each route, keyed by route identifier.
+        let mut route_parts: HashMap<String, Vec<(Vec<Point2D>, Vec<f64>)>> = HashMap::new();
+        for record_num in 0..routes.num_records {
+            reporter.check_cancelled()?;
+            let record = routes.get_record(record_num);
+            if record.shape_type == ShapeType::Null {
+                continue;
+            }
+            let key = route_key(&format!("{}", routes.attributes.get_value(record_num, &route_field)));
+            let use_m = record.has_m_data() && record.m_array.iter().all(|m| *m > -1.0e38);
+            let mut length = 0f64;
+            for part in 0..record.num_parts as usize {
+                let part_start = record.parts[part] as usize;
+                let part_end = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize
+                } else {
+                    record.num_points as usize
+                };
+                let points = record.points[part_start..part_end].to_vec();
+                let measures = if use_m {
+                    record.m_array[part_start..part_end].to_vec()
+                } else {
+                    let d = cumulative_distances(&points, length);
+                    length = *d.last().unwrap_or(&length);
+                    d
+                };
+                route_parts
+                    .entry(key.clone())
+                    .or_insert(vec![])
+                    .push((points, measures));
+            }
+        }
+
+        // create output file
+        let output_type = if line_events {
+            ShapeType::PolyLineM
+        } else {
+            ShapeType::Point
+        };
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &routes, output_type, false)?;
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 8u8, 0u8));
+        let table_fields = infer_field_types(&headers, &rows);
+        let mut copied_columns = vec![];
+        for i in 0..table_fields.len() {
+            if !table_fields[i].name.eq_ignore_ascii_case("FID") {
+                output.attributes.add_field(&table_fields[i]);
+                copied_columns.push(i);
+            }
+        }
+
+        let mut fid = 1i32;
+        let mut num_route_errors = 0;
+        let mut num_measure_errors = 0;
+        let num_events = rows.len();
+        for (row_num, row) in rows.iter().enumerate() {
+            let parts = match route_parts.get(&route_key(&row[route_col])) {
+                Some(p) => p,
+                None => {
+                    num_route_errors += 1;
+                    continue;
+                }
+            };
+
+            let mut located = false;
+            if line_events {
+                let from_m = row[from_col].trim().parse::<f64>();
+                let to_m = row[to_col].trim().parse::<f64>();
+                if let (Ok(from_m), Ok(to_m)) = (from_m, to_m) {
+                    let mut sfg = ShapefileGeometry::new(ShapeType::PolyLineM);
+                    for (points, measures) in parts {
+                        if let Some((pts, ms)) = extract_measure_range(points, measures, from_m, to_m) {
+                            sfg.add_partm(&pts, &ms);
+                        }
+                    }
+                    if sfg.num_parts > 0 {
+                        output.add_record(sfg);
+                        located = true;
+                    }
+                }
+            } else if let Ok(m) = row[measure_col].trim().parse::<f64>() {
+                let offset = match offset_col {
+                    Some(c) => row[c].trim().parse::<f64>().unwrap_or(0f64),
+                    None => 0f64,
+                };
+                for (points, measures) in parts {
+                    if let Some((p, seg)) = locate_measure(points, measures, m) {
+                        let p = if points.len() > 1 {
+                            offset_point(&p, &points[seg], &points[seg + 1], offset)
+                        } else {
+                            p
+                        };
+                        output.add_point_record(p.x, p.y);
+                        located = true;
+                        break;
+                    }
+                }
+            }
+
+            if located {
+                let mut atts = vec![FieldData::Int(fid)];
+                for &c in &copied_columns {
+                    atts.push(parse_value(&row[c], &table_fields[c]));
+                }
+                output.attributes.add_record(atts, false);
+                fid += 1;
+            } else {
+                num_measure_errors += 1;
+            }
+
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * (row_num + 1) as f64 / num_events as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if output.num_records == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "None of the events could be located along the routes.",
+            ));
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Events located: {} of {}", fid - 1, num_events);
+            if num_route_errors > 0 {
+                println!("Location errors (route not found): {}", num_route_errors);
+            }
+            if num_measure_errors > 0 {
+                println!("Location errors (measure out of range or invalid): {}", num_measure_errors);
+            }
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+/// Normalizes a route identifier so that numeric identifiers match regardless of formatting.
+fn route_key(s: &str) -> String {
+    let s = s.trim();
+    match s.parse::<f64>() {
+        Ok(v) => format!("{}", v),
+        Err(_) => s.to_string(),
+    }
+}
+
+/// Reads a delimited text table with a header row.
+fn read_csv_table(file_name: &str) -> Result<(Vec<String>, Vec<Vec<String>>), Error> {
+    let f = match File::open(file_name) {
+        Ok(v) => v,
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Error opening the events table file.",
+            ));
+        }
+    };
+    let f = BufReader::new(f);
+    let mut headers: Vec<String> = vec![];
+    let mut rows: Vec<Vec<String>> = vec![];
+    let mut delimiter = ",";
+    for line in f.lines() {
+        let line_unwrapped = line?;
+        if line_unwrapped.trim().is_empty() {
+            continue;
+        }
+        let mut line_vec = line_unwrapped.split(delimiter).collect::<Vec<&str>>();
+        if headers.is_empty() && line_vec.len() == 1 {
+            for d in [";", "\t"] {
+                if line_unwrapped.contains(d) {
+                    delimiter = d;
+                    line_vec = line_unwrapped.split(delimiter).collect::<Vec<&str>>();
+                    break;
+                }
+            }
+        }
+        let values: Vec<String> = line_vec
+            .iter()
+            .map(|v| v.trim().trim_matches('"').to_string())
+            .collect();
+        if headers.is_empty() {
+            headers = values;
+        } else {
+            if values.len() != headers.len() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Not all records in the events table are the same length. Cannot read the table.",
+                ));
+            }
+            rows.push(values);
+        }
+    }
+    if headers.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The events table is empty.",
+        ));
+    }
+    Ok((headers, rows))
+}
+
+/// Determines an attribute field definition for each of the columns in a table.
+fn infer_field_types(headers: &[String], rows: &[Vec<String>]) -> Vec<AttributeField> {
+    let mut fields = Vec::with_capacity(headers.len());
+    for c in 0..headers.len() {
+        let mut is_int = true;
+        let mut is_real = true;
+        let mut length = 1usize;
+        let mut precision = 0usize;
+        for row in rows {
+            let v = &row[c];
+            length = length.max(v.len());
+            if v.is_empty() {
+                continue;
+            }
+            if v.parse::<i32>().is_err() {
+                is_int = false;
+            }
+            if v.parse::<f64>().is_err() {
+                is_real = false;
+            } else if let Some(p) = v.find('.') {
+                precision = precision.max(v.len() - p - 1);
+            }
+        }
+        let field = if is_int {
+            AttributeField::new(&headers[c], FieldDataType::Int, length.min(18) as u8, 0u8)
+        } else if is_real {
+            AttributeField::new(
+                &headers[c],
+                FieldDataType::Real,
+                (length + 2).min(24) as u8,
+                precision.min(15) as u8,
+            )
+        } else {
+            AttributeField::new(&headers[c], FieldDataType::Text, length.min(254) as u8, 0u8)
+        };
+        fields.push(field);
+    }
+    fields
+}
+
+fn parse_value(s: &str, field: &AttributeField) -> FieldData {
+    if s.is_empty() {
+        return FieldData::Null;
+    }
+    match field.field_type {
+        'N' if field.decimal_count == 0 => match s.parse::<i32>() {
+            Ok(v) => FieldData::Int(v),
+            Err(_) => FieldData::Null,
+        },
+        'N' | 'F' => match s.parse::<f64>() {
+            Ok(v) => FieldData::Real(v),
+            Err(_) => FieldData::Null,
+        },
+        _ => FieldData::Text(s.to_string()),
+    }
+}
@@ -0,0 +1,290 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::median_center;
+use super::point_groups::read_point_groups;
+use crate::tools::*;
+use whitebox_vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool calculates the median center of the points in an input vector points layer, i.e. the
+/// location that minimizes the sum of the (weighted) Euclidean distances to all of the points. This
+/// is sometimes referred to as the geometric median or the point of minimum aggregate travel. Unlike
+/// the mean center (`MeanCenter`), the median center is robust to the presence of outlying points.
+/// It is calculated using the iterative algorithm of Weiszfeld (1937), starting from the mean center
+/// and continuing until the location changes by less than one billionth of the extent of the points.
+///
+/// Points may optionally be weighted using a numeric attribute field (`--weight_field`), e.g. the
+/// number of cases at each location. Features with null or negative weights are ignored. If a case
+/// field (`--case_field`) is specified, a separate median center is calculated for each unique value
+/// of the field.
+///
+/// The output is a vector points file containing one point for each case. The attribute table
+/// contains the case field value (if specified), the number of points (N), the sum of the weights
+/// (SUM_WEIGHT), the coordinates of the median center (X and Y), and the number of iterations
+/// required to locate it (ITER).
+///
+/// # References
+/// Weiszfeld, E. (1937). Sur le point pour lequel la somme des distances de n points donnés est
+/// minimum. Tohoku Mathematical Journal, 43, 355-386.
+///
+/// # See Also
+/// `MeanCenter`, `StandardDistance`, `StandardDeviationalEllipse`, `Medoid`
+pub struct MedianCenter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl MedianCenter {
+    pub fn new() -> MedianCenter {
+        // public constructor
+        let name = "MedianCenter".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Calculates the (weighted) median center of a vector points layer, optionally by case."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Points File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector points file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector points file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Weight Field".to_owned(),
+            flags: vec!["--weight_field".to_owned()],
+            description: "Numeric field used to weight the points (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Case Field".to_owned(),
+            flags: vec!["--case_field".to_owned()],
+            description: "Field used to group the points into cases (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=cases.shp -o=median_center.shp --weight_field=COUNT --case_field=YEAR",
+            short_exe, name
+        ).replace("*", &sep);
+
+        MedianCenter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for MedianCenter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let weight_field = parsed.get_string("weight_field").unwrap_or_default();
+        let case_field = parsed.get_string("case_field").unwrap_or_default();
+
+        // read the arguments
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+        let groups = read_point_groups(&input, &weight_field, &case_field, reporter)?;
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, ShapeType::Point, false)?;
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        if !case_field.is_empty() {
+            let i = input.attributes.get_field_num(&case_field).unwrap();
+            output.attributes.add_field(input.attributes.get_field(i));
+        }
+        output
+            .attributes
+            .add_field(&AttributeField::new("N", FieldDataType::Int, 9u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("SUM_WEIGHT", FieldDataType::Real, 18u8, 4u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("X", FieldDataType::Real, 18u8, 6u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("Y", FieldDataType::Real, 18u8, 6u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("ITER", FieldDataType::Int, 6u8, 0u8));
+
+        let mut fid = 1i32;
+        for group in &groups {
+            reporter.check_cancelled()?;
+            let sum_weight: f64 = group.weights.iter().sum();
+            if sum_weight <= 0f64 {
+                continue;
+            }
+            let (mut min_x, mut max_x) = (f64::INFINITY, f64::NEG_INFINITY);
+            let (mut min_y, mut max_y) = (f64::INFINITY, f64::NEG_INFINITY);
+            for p in &group.points {
+                min_x = min_x.min(p.x);
+                max_x = max_x.max(p.x);
+                min_y = min_y.min(p.y);
+                max_y = max_y.max(p.y);
+            }
+            let tolerance = 1e-9 * (max_x - min_x).max(max_y - min_y).max(1e-9);
+            let (center, iterations) =
+                median_center(&group.points, &group.weights, tolerance, 10000);
+            output.add_point_record(center.x, center.y);
+            let mut atts = vec![FieldData::Int(fid)];
+            if !case_field.is_empty() {
+                atts.push(group.case_value.clone());
+            }
+            atts.push(FieldData::Int(group.points.len() as i32));
+            atts.push(FieldData::Real(sum_weight));
+            atts.push(FieldData::Real(center.x));
+            atts.push(FieldData::Real(center.y));
+            atts.push(FieldData::Int(iterations as i32));
+            output.attributes.add_record(atts, false);
+            fid += 1;
+        }
+
+        if output.num_records == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input file does not contain any points with positive weights.",
+            ));
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
@@ -142,59 +142,21 @@ impl WhiteboxTool for MergeLineSegments {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut precision = std::f64::EPSILON;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val.contains("-input") {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-snap" {
-                precision = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if precision == 0f64 {
-                    precision = std::f64::EPSILON;
-                }
+        if let Some(value) = parsed.get_f64("snap") {
+            precision = value;
+            if precision == 0f64 {
+                precision = std::f64::EPSILON;
             }
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -211,12 +173,8 @@ impl WhiteboxTool for MergeLineSegments {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -263,6 +221,7 @@ impl WhiteboxTool for MergeLineSegments {
         let mut last_point_in_part: usize;
         let mut polylines: Vec<Polyline> = vec![];
         for record_num in 0..input.num_records {
+            reporter.check_cancelled()?;
             let record = input.get_record(record_num);
             for part in 0..record.num_parts as usize {
                 first_point_in_part = record.parts[part] as usize;
@@ -420,10 +379,11 @@ impl WhiteboxTool for MergeLineSegments {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -490,10 +450,11 @@ impl WhiteboxTool for MergeLineSegments {
                 }
                 output.attributes.add_record(out_atts, false);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
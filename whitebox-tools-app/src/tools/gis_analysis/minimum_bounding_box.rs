@@ -12,7 +12,7 @@ use crate::tools::*;
 use whitebox_vector::ShapefileGeometry;
 use whitebox_vector::*;
 use std::env;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool delineates the minimum bounding box (MBB) for a group of vectors. The MBB is the smallest box to
@@ -74,12 +74,12 @@ impl MinimumBoundingBox {
 
         parameters.push(ToolParameter {
             name: "Find bounding rectangles around each individual feature.".to_owned(),
-            flags: vec!["--features".to_owned()],
+            flags: vec!["--feature".to_owned(), "--features".to_owned()],
             description:
                 "Find the minimum bounding rectangles around each individual vector feature"
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true,
         });
 
@@ -151,64 +151,28 @@ impl WhiteboxTool for MinimumBoundingBox {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut individual_feature_hulls = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let mut individual_feature_hulls = parsed.get_bool("features");
         let mut min_criterion = MinimizationCriterion::Area;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-criterion" {
-                let criteria_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                min_criterion = if criteria_str.contains("len") {
-                    MinimizationCriterion::Length
-                } else if criteria_str.contains("wi") {
-                    MinimizationCriterion::Width
-                } else if criteria_str.contains("per") {
-                    MinimizationCriterion::Perimeter
-                } else {
-                    MinimizationCriterion::Area
-                };
-            } else if flag_val == "-features" || flag_val == "-feature" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    individual_feature_hulls = true;
-                }
-            }
+        if let Some(value) = parsed.get_string("criterion") {
+            let criteria_str = value.to_string();
+            min_criterion = if criteria_str.contains("len") {
+                MinimizationCriterion::Length
+            } else if criteria_str.contains("wi") {
+                MinimizationCriterion::Width
+            } else if criteria_str.contains("per") {
+                MinimizationCriterion::Perimeter
+            } else {
+                MinimizationCriterion::Area
+            };
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -225,13 +189,9 @@ impl WhiteboxTool for MinimumBoundingBox {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Shapefile::read(&input_file)?;
 
@@ -264,11 +224,12 @@ impl WhiteboxTool for MinimumBoundingBox {
                 let atts = input.attributes.get_record(record_num);
                 output.attributes.add_record(atts.clone(), false);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -301,11 +262,12 @@ impl WhiteboxTool for MinimumBoundingBox {
                     points.push(Point2D::new(record.points[i].x, record.points[i].y));
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                     if progress != old_progress {
-                        println!("Reading points: {}%", progress);
+                        reporter.progress("Reading points", progress);
                         old_progress = progress;
                     }
                 }
@@ -1,16 +1,20 @@
 // private sub-module defined in other files
 mod aggregate_raster;
+mod areal_interpolation;
 mod average_overlay;
 mod block_maximum;
 mod block_minimum;
+mod block_statistics;
 mod boundary_shape_complexity;
 mod buffer_raster;
 // mod buffer_vector;
+mod calibrate_routes;
 mod centroid;
 mod centroid_vector;
 mod clip;
 mod clip_raster_to_polygon;
 mod clump;
+mod combine;
 mod compactness_ratio;
 mod construct_vector_tin;
 mod cost_allocation;
@@ -21,6 +25,7 @@ mod create_hexagonal_vector_grid;
 mod create_plane;
 mod create_rectangular_vector_grid;
 mod difference;
+mod disaggregate_raster;
 mod dissolve;
 mod edge_proportion;
 mod eliminate_coincident_points;
@@ -32,6 +37,7 @@ mod euclidean_distance;
 mod extend_vector_lines;
 mod extract_nodes;
 mod extract_raster_values_at_points;
+mod fill_nodata_from_rasters;
 mod filter_raster_features_by_area;
 mod find_lowest_or_highest_points;
 mod find_patch_edge_cells;
@@ -40,11 +46,15 @@ mod hole_proportion;
 mod idw_interpolation;
 mod intersect;
 mod layer_footprint;
+mod least_cost_path_density;
 mod line_intersections;
 mod linearity_index;
+mod locate_route_events;
 mod lowest_pos;
 mod max_abs_overlay;
 mod max_overlay;
+mod mean_center;
+mod median_center;
 mod medoid;
 mod merge_line_segments;
 mod min_abs_overlay;
@@ -63,6 +73,7 @@ mod percent_greater_than;
 mod percent_less_than;
 mod perimeter_area_ratio;
 mod pick_from_list;
+mod point_groups;
 mod polygon_area;
 mod polygon_long_axis;
 mod polygon_perimeter;
@@ -72,6 +83,7 @@ mod radial_basis_function_interpolation;
 mod radius_of_gyration;
 mod raster_area;
 mod raster_cell_assignment;
+mod raster_mask;
 mod raster_perimeter;
 mod reclass;
 mod reclass_equal_interval;
@@ -80,7 +92,10 @@ mod related_circumscribing_circle;
 mod shape_complexity_index;
 mod shape_complexity_raster;
 mod smooth_vectors;
+mod snap_vectors;
 mod split_with_lines;
+mod standard_deviational_ellipse;
+mod standard_distance;
 mod sum_overlay;
 mod symmetrical_difference;
 mod tin_gridding;
@@ -93,17 +108,21 @@ mod weighted_sum;
 
 // exports identifiers from private sub-modules in the current module namespace
 pub use self::aggregate_raster::AggregateRaster;
+pub use self::areal_interpolation::ArealInterpolation;
 pub use self::average_overlay::AverageOverlay;
 pub use self::block_maximum::BlockMaximumGridding;
 pub use self::block_minimum::BlockMinimumGridding;
+pub use self::block_statistics::BlockStatistics;
 pub use self::boundary_shape_complexity::BoundaryShapeComplexity;
 pub use self::buffer_raster::BufferRaster;
 // pub use self::buffer_vector::BufferVector;
+pub use self::calibrate_routes::CalibrateRoutes;
 pub use self::centroid::Centroid;
 pub use self::centroid_vector::CentroidVector;
 pub use self::clip::Clip;
 pub use self::clip_raster_to_polygon::ClipRasterToPolygon;
 pub use self::clump::Clump;
+pub use self::combine::Combine;
 pub use self::compactness_ratio::CompactnessRatio;
 pub use self::construct_vector_tin::ConstructVectorTIN;
 pub use self::cost_allocation::CostAllocation;
@@ -114,6 +133,7 @@ pub use self::create_hexagonal_vector_grid::CreateHexagonalVectorGrid;
 pub use self::create_plane::CreatePlane;
 pub use self::create_rectangular_vector_grid::CreateRectangularVectorGrid;
 pub use self::difference::Difference;
+pub use self::disaggregate_raster::DisaggregateRaster;
 pub use self::dissolve::Dissolve;
 pub use self::edge_proportion::EdgeProportion;
 pub use self::eliminate_coincident_points::EliminateCoincidentPoints;
@@ -125,6 +145,7 @@ pub use self::euclidean_distance::EuclideanDistance;
 pub use self::extend_vector_lines::ExtendVectorLines;
 pub use self::extract_nodes::ExtractNodes;
 pub use self::extract_raster_values_at_points::ExtractRasterValuesAtPoints;
+pub use self::fill_nodata_from_rasters::FillNodataFromRasters;
 pub use self::filter_raster_features_by_area::FilterRasterFeaturesByArea;
 pub use self::find_lowest_or_highest_points::FindLowestOrHighestPoints;
 pub use self::find_patch_edge_cells::FindPatchOrClassEdgeCells;
@@ -133,11 +154,15 @@ pub use self::hole_proportion::HoleProportion;
 pub use self::idw_interpolation::IdwInterpolation;
 pub use self::intersect::Intersect;
 pub use self::layer_footprint::LayerFootprint;
+pub use self::least_cost_path_density::LeastCostPathDensity;
 pub use self::line_intersections::LineIntersections;
 pub use self::linearity_index::LinearityIndex;
+pub use self::locate_route_events::LocateRouteEvents;
 pub use self::lowest_pos::LowestPosition;
 pub use self::max_abs_overlay::MaxAbsoluteOverlay;
 pub use self::max_overlay::MaxOverlay;
+pub use self::mean_center::MeanCenter;
+pub use self::median_center::MedianCenter;
 pub use self::medoid::Medoid;
 pub use self::merge_line_segments::MergeLineSegments;
 pub use self::min_abs_overlay::MinAbsoluteOverlay;
@@ -173,7 +198,10 @@ pub use self::related_circumscribing_circle::RelatedCircumscribingCircle;
 pub use self::shape_complexity_index::ShapeComplexityIndex;
 pub use self::shape_complexity_raster::ShapeComplexityIndexRaster;
 pub use self::smooth_vectors::SmoothVectors;
+pub use self::snap_vectors::SnapVectors;
 pub use self::split_with_lines::SplitWithLines;
+pub use self::standard_deviational_ellipse::StandardDeviationalEllipse;
+pub use self::standard_distance::StandardDistance;
 pub use self::sum_overlay::SumOverlay;
 pub use self::symmetrical_difference::SymmetricalDifference;
 pub use self::tin_gridding::TINGridding;
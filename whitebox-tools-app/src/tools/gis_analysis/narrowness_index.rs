@@ -11,7 +11,7 @@ use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tools calculates a type of shape narrowness index (*NI*) for raster objects. The index is equal to:
@@ -126,40 +126,11 @@ impl WhiteboxTool for NarrownessIndex {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -172,18 +143,12 @@ impl WhiteboxTool for NarrownessIndex {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...");
@@ -239,7 +204,7 @@ impl WhiteboxTool for NarrownessIndex {
         for row in 0..rows {
             for col in 0..columns {
                 z = input[(row, col)];
-                if z == 0.0 || z == nodata {
+                if z == 0.0 || is_nodata(z, nodata) {
                     output[(row, col)] = 0.0;
                 } else {
                     bin = (z - min_val).floor() as usize;
@@ -262,10 +227,11 @@ impl WhiteboxTool for NarrownessIndex {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Initializing Rasters: {}%", progress);
+                    reporter.progress("Initializing Rasters", progress);
                     old_progress = progress;
                 }
             }
@@ -281,7 +247,7 @@ impl WhiteboxTool for NarrownessIndex {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = output[(y, x)];
-                        if z2 != out_nodata {
+                        if !is_nodata(z2, out_nodata) {
                             h = match i {
                                 0 => 2.0 * r_x[(y, x)] + 1.0,
                                 1 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
@@ -304,10 +270,11 @@ impl WhiteboxTool for NarrownessIndex {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (1 of 4): {}%", progress);
+                    reporter.progress("Progress (1 of 4)", progress);
                     old_progress = progress;
                 }
             }
@@ -323,7 +290,7 @@ impl WhiteboxTool for NarrownessIndex {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = output[(y, x)];
-                        if z2 != out_nodata {
+                        if !is_nodata(z2, out_nodata) {
                             h = match i {
                                 5 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
                                 4 => 2.0 * r_x[(y, x)] + 1.0,
@@ -346,10 +313,11 @@ impl WhiteboxTool for NarrownessIndex {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (rows - row) as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (2 of 4): {}%", progress);
+                    reporter.progress("Progress (2 of 4)", progress);
                     old_progress = progress;
                 }
             }
@@ -358,7 +326,7 @@ impl WhiteboxTool for NarrownessIndex {
         for row in 0..rows {
             for col in 0..columns {
                 z = input[(row, col)];
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     if z != 0f64 {
                         output[(row, col)] = output[(row, col)].sqrt() * cell_size;
                         bin = (z - min_val).floor() as usize;
@@ -372,10 +340,11 @@ impl WhiteboxTool for NarrownessIndex {
                     output[(row, col)] = out_nodata;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (3 of 4): {}%", progress);
+                    reporter.progress("Progress (3 of 4)", progress);
                     old_progress = progress;
                 }
             }
@@ -390,15 +359,16 @@ impl WhiteboxTool for NarrownessIndex {
         for row in 0..rows {
             for col in 0..columns {
                 z = input[(row, col)];
-                if z != nodata && z != 0f64 {
+                if !is_nodata(z, nodata) && z != 0f64 {
                     bin = (z - min_val).floor() as usize;
                     output[(row, col)] = max_width[bin];
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (4 of 4): {}%", progress);
+                    reporter.progress("Progress (4 of 4)", progress);
                     old_progress = progress;
                 }
             }
@@ -13,7 +13,6 @@ use crate::tools::*;
 use whitebox_vector::{FieldData, ShapeType, ShapeTypeDimension, Shapefile};
 use kdtree::distance::squared_euclidean;
 use kdtree::KdTree;
-use num_cpus;
 use std::collections::HashMap;
 use std::env;
 use std::f64;
@@ -21,7 +20,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to interpolate a set of input vector points (`--input`) onto a raster grid using
 /// Sibson's (1981) natural neighbour method. Similar to inverse-distance-weight interpolation (`IdwInterpolation`),
@@ -117,7 +116,7 @@ impl NaturalNeighbourInterpolation {
 
         parameters.push(ToolParameter{
             name: "Cell Size (optional)".to_owned(), 
-            flags: vec!["--cell_size".to_owned()], 
+            flags: vec!["--resolution".to_owned(), "--cell_size".to_owned()], 
             description: "Optionally specified cell size of output raster. Not used when base raster is specified.".to_owned(),
             parameter_type: ParameterType::Float,
             default_value: None,
@@ -138,7 +137,7 @@ impl NaturalNeighbourInterpolation {
             flags: vec!["--clip".to_owned()],
             description: "Clip the data to the convex hull of the points?".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -210,82 +209,24 @@ impl WhiteboxTool for NaturalNeighbourInterpolation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
         let mut field_name = String::new();
-        let mut use_z = false;
+        let use_z = parsed.get_bool("use_z");
         let mut use_field = false;
-        let mut output_file: String = "".to_string();
-        let mut grid_res: f64 = 0.0;
-        let mut base_file = String::new();
-        let mut clip_to_hull = false;
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let grid_res: f64 = parsed.get_f64("cell_size").unwrap_or(0.0);
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+        let clip_to_hull = parsed.get_bool("clip");
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                field_name = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                use_field = true;
-            } else if flag_val.contains("use_z") {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    use_z = true;
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" || flag_val == "-cell_size" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val.contains("clip") {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    clip_to_hull = true;
-                }
-            } else if flag_val == "-base" {
-                base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
+        if let Some(value) = parsed.get_string("field") {
+            field_name = value.to_string();
+            use_field = true;
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -302,13 +243,9 @@ impl WhiteboxTool for NaturalNeighbourInterpolation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Shapefile::read(&input_file)?;
 
@@ -357,9 +294,7 @@ impl WhiteboxTool for NaturalNeighbourInterpolation {
 
         let nodata = -32768.0f64;
         let mut output = if !base_file.trim().is_empty() || grid_res == 0f64 {
-            if !base_file.contains(&sep) && !base_file.contains("/") {
-                base_file = format!("{}{}", working_directory, base_file);
-            }
+            base_file = resolve_input_path(&base_file, working_directory)?;
             let mut base = Raster::new(&base_file, "r")?;
             base.configs.nodata = nodata;
             Raster::initialize_using_file(&output_file, &base)
@@ -437,11 +372,12 @@ impl WhiteboxTool for NaturalNeighbourInterpolation {
                 p += 1;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Reading points: {}%", progress);
+                    reporter.progress("Reading points", progress);
                     old_progress = progress;
                 }
             }
@@ -470,11 +406,12 @@ impl WhiteboxTool for NaturalNeighbourInterpolation {
             if !point_edge_map.contains_key(&endpoint) || delaunay.halfedges[edge] == EMPTY {
                 point_edge_map.insert(endpoint, edge);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * edge as f64 / (delaunay.triangles.len() - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -492,12 +429,8 @@ impl WhiteboxTool for NaturalNeighbourInterpolation {
         let tree = Arc::new(tree);
         let hull_vertices = Arc::new(hull_vertices);
         let point_edge_map = Arc::new(point_edge_map);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let points = points.clone();
@@ -507,7 +440,7 @@ impl WhiteboxTool for NaturalNeighbourInterpolation {
             let hull_vertices = hull_vertices.clone();
             let point_edge_map = point_edge_map.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut px, mut py): (f64, f64);
                 let mut previous_nn = EMPTY;
                 let mut delaunay2: Triangulation;
@@ -713,7 +646,9 @@ impl WhiteboxTool for NaturalNeighbourInterpolation {
                             };
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -721,10 +656,11 @@ impl WhiteboxTool for NaturalNeighbourInterpolation {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Interpolating: {}%", progress);
+                    reporter.progress("Interpolating", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,101 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 17/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+NOTES: Shared point-grouping used by MeanCenter, MedianCenter, StandardDistance and
+StandardDeviationalEllipse.
+*/
+
+use whitebox_common::structures::Point2D;
+use crate::tools::ProgressReporter;
+use whitebox_vector::*;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+
+/// The points of a vector points layer that share a case value, with the weight of each point.
+pub(super) struct PointGroup {
+    pub case_value: FieldData,
+    pub points: Vec<Point2D>,
+    pub weights: Vec<f64>,
+}
+
+/// Reads the points and weights of a vector points layer, grouped by case in order of first appearance.
+pub(super) fn read_point_groups(
+    input: &Shapefile,
+    weight_field: &str,
+    case_field: &str,
+    reporter: &dyn ProgressReporter,
+) -> Result<Vec<PointGroup>, Error> {
+    let base_type = input.header.shape_type.base_shape_type();
+    if base_type != ShapeType::Point && base_type != ShapeType::MultiPoint {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The input vector data must be of a POINT or MULTIPOINT base shape type.",
+        ));
+    }
+    if !weight_field.is_empty() {
+        match input.attributes.get_field_num(weight_field) {
+            Some(i) if input.attributes.is_field_numeric(i) => {}
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The weight field must be a numeric field of the input attribute table.",
+                ))
+            }
+        }
+    }
+    if !case_field.is_empty() && input.attributes.get_field_num(case_field).is_none() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The case field does not exist in the input attribute table.",
+        ));
+    }
+
+    let mut groups: Vec<PointGroup> = vec![];
+    let mut lookup: HashMap<String, usize> = HashMap::new();
+    for record_num in 0..input.num_records {
+        reporter.check_cancelled()?;
+        let record = input.get_record(record_num);
+        if record.shape_type == ShapeType::Null {
+            continue;
+        }
+        let weight = if weight_field.is_empty() {
+            1f64
+        } else {
+            match input.attributes.get_value(record_num, weight_field) {
+                FieldData::Int(v) => v as f64,
+                FieldData::Real(v) => v,
+                _ => -1f64,
+            }
+        };
+        if weight < 0f64 || !weight.is_finite() {
+            continue;
+        }
+        let case_value = if case_field.is_empty() {
+            FieldData::Null
+        } else {
+            input.attributes.get_value(record_num, case_field)
+        };
+        let key = format!("{}", case_value);
+        let g = match lookup.get(&key) {
+            Some(&g) => g,
+            None => {
+                lookup.insert(key, groups.len());
+                groups.push(PointGroup {
+                    case_value,
+                    points: vec![],
+                    weights: vec![],
+                });
+                groups.len() - 1
+            }
+        };
+        for p in &record.points {
+            groups[g].points.push(*p);
+            groups[g].weights.push(weight);
+        }
+    }
+    Ok(groups)
+}
@@ -52,7 +52,7 @@ impl Polygonize {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input Vector Lines File".to_owned(),
-            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--inputs".to_owned()],
             description: "Input vector polyline file.".to_owned(),
             parameter_type: ParameterType::FileList(ParameterFileType::Vector(
                 VectorGeometryType::Line,
@@ -140,43 +140,14 @@ impl WhiteboxTool for Polygonize {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files = String::new();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val.contains("-input") {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -193,9 +164,7 @@ impl WhiteboxTool for Polygonize {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut cmd = input_files.split(";");
         let mut vec = cmd.collect::<Vec<&str>>();
@@ -221,9 +190,7 @@ impl WhiteboxTool for Polygonize {
         for value in vec {
             if !value.trim().is_empty() {
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
 
                 let input = Shapefile::read(&input_file)?;
                 projection = input.projection.clone();
@@ -298,11 +265,12 @@ impl WhiteboxTool for Polygonize {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num1 + 1) as f64 / in_polylines.len() as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding line intersections: {}%", progress);
+                    reporter.progress("Finding line intersections", progress);
                     old_progress = progress;
                 }
             }
@@ -475,10 +443,11 @@ impl WhiteboxTool for Polygonize {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding acyclic arcs: {}%", progress);
+                    reporter.progress("Finding acyclic arcs", progress);
                     old_progress = progress;
                 }
             }
@@ -794,10 +763,11 @@ impl WhiteboxTool for Polygonize {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding polygons: {}%", progress);
+                    reporter.progress("Finding polygons", progress);
                     old_progress = progress;
                 }
             }
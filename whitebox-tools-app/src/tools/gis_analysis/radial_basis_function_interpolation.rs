@@ -14,14 +14,13 @@ use whitebox_vector::{FieldData, ShapeType, Shapefile};
 use kdtree::distance::squared_euclidean;
 use kdtree::KdTree;
 use nalgebra::DVector;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool interpolates vector points into a raster surface using a radial basis function (RBF) scheme.
 pub struct RadialBasisFunctionInterpolation {
@@ -141,7 +140,7 @@ impl RadialBasisFunctionInterpolation {
 
         parameters.push(ToolParameter{
             name: "Cell Size (optional)".to_owned(), 
-            flags: vec!["--cell_size".to_owned()], 
+            flags: vec!["--resolution".to_owned(), "--cell_size".to_owned()], 
             description: "Optionally specified cell size of output raster. Not used when base raster is specified.".to_owned(),
             parameter_type: ParameterType::Float,
             default_value: None,
@@ -215,133 +214,34 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut field_name = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let field_name = parsed.get_string("field").unwrap_or_default();
         // let mut use_field = false;
-        let mut use_z = false;
-        let mut output_file = String::new();
-        let mut grid_res = 0f64;
-        let mut base_file = String::new();
-        let mut radius = 0f64;
-        let mut min_points = 0usize;
+        let use_z = parsed.get_bool("use_z");
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let grid_res = parsed.get_f64("cell_size").unwrap_or(0f64);
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+        let mut radius = parsed.get_f64("radius").unwrap_or(0f64);
+        let min_points = parsed.get_usize("min_points")?.unwrap_or(0usize);
         let mut func_type = String::from("ThinPlateSpline").to_lowercase();
         let mut poly_order = 0usize;
-        let mut weight = 0.1f64;
+        let weight = parsed.get_f64("weight").unwrap_or(0.1f64);
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_string("func_type") {
+            func_type = value.to_string().to_lowercase();
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                field_name = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            // use_field = true;
-            } else if flag_val == "-use_z" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    use_z = true;
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" || flag_val == "-cell_size" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-base" {
-                base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-radius" {
-                radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-min_points" {
-                min_points = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-func_type" {
-                func_type = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-            } else if flag_val == "-poly_order" {
-                let s = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-                poly_order = if s.contains("none") {
-                    0usize
-                } else if s.contains("const") {
-                    1usize
-                } else {
-                    2usize
-                };
-            } else if flag_val == "-weight" {
-                weight = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
+        if let Some(value) = parsed.get_string("poly_order") {
+            let s = value.to_string().to_lowercase();
+            poly_order = if s.contains("none") {
+                0usize
+            } else if s.contains("const") {
+                1usize
+            } else {
+                2usize
+            };
         }
 
         if verbose {
@@ -355,8 +255,6 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -373,12 +271,8 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
             Basis::InverseMultiQuadric(weight)
         };
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         radius = radius * radius; // squared distances are used
 
@@ -445,11 +339,12 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Reading points: {}%", progress);
+                    reporter.progress("Reading points", progress);
                     old_progress = progress;
                 }
             }
@@ -478,9 +373,7 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
         let nodata = -32768.0f64;
 
         let mut output = if !base_file.trim().is_empty() || grid_res == 0f64 {
-            if !base_file.contains(&sep) && !base_file.contains("/") {
-                base_file = format!("{}{}", working_directory, base_file);
-            }
+            base_file = resolve_input_path(&base_file, working_directory)?;
             let mut base = Raster::new(&base_file, "r")?;
             base.configs.nodata = nodata;
             Raster::initialize_using_file(&output_file, &base)
@@ -530,12 +423,8 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
         let z_values = Arc::new(z_values);
         let hull = Arc::new(hull);
         let tree = Arc::new(tree);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let points = points.clone();
@@ -543,7 +432,7 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
             let hull = hull.clone();
             let tree = tree.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut x, mut y): (f64, f64);
                 let mut z: f64;
                 let mut point_num;
@@ -578,7 +467,9 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
                             }
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -586,10 +477,11 @@ impl WhiteboxTool for RadialBasisFunctionInterpolation {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
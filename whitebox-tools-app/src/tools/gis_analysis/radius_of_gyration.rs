@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This can be used to calculate the radius of gyration (RoG) for the polygon
 /// features within a raster image. RoG measures how far across the landscape a polygon
@@ -133,45 +132,12 @@ impl WhiteboxTool for RadiusOfGyration {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut text_output = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-text_output" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    text_output = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let text_output = parsed.get_bool("text_output");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -184,17 +150,11 @@ impl WhiteboxTool for RadiusOfGyration {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -213,17 +173,13 @@ impl WhiteboxTool for RadiusOfGyration {
         let max_val = input.configs.maximum.ceil() as usize;
         let range = max_val - min_val;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut a: usize;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -232,14 +188,16 @@ impl WhiteboxTool for RadiusOfGyration {
                     let mut total_n = vec![0usize; range + 1];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z > 0f64 && z != nodata {
+                        if z > 0f64 && !is_nodata(z, nodata) {
                             a = (z - min_val as f64) as usize;
                             total_columns[a] += col as usize;
                             total_rows[a] += row as usize;
                             total_n[a] += 1usize;
                         }
                     }
-                    tx.send((total_columns, total_rows, total_n)).unwrap();
+                    if tx.send((total_columns, total_rows, total_n)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -257,10 +215,11 @@ impl WhiteboxTool for RadiusOfGyration {
                 total_n[a] += n[a];
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 3): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -283,14 +242,14 @@ impl WhiteboxTool for RadiusOfGyration {
             let centroid_x = centroid_x.clone();
             let centroid_y = centroid_y.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut a: usize;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut gyradius = vec![0f64; range + 1];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z > 0f64 && z != nodata {
+                        if z > 0f64 && !is_nodata(z, nodata) {
                             a = (z - min_val as f64) as usize;
                             gyradius[a] = ((col as f64 - centroid_x[a]) * resolution_x)
                                 * ((col as f64 - centroid_x[a]) * resolution_x)
@@ -298,7 +257,9 @@ impl WhiteboxTool for RadiusOfGyration {
                                     * ((row as f64 - centroid_y[a]) * resolution_y)
                         }
                     }
-                    tx.send(gyradius).unwrap();
+                    if tx.send(gyradius).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -310,10 +271,11 @@ impl WhiteboxTool for RadiusOfGyration {
                 gyradius[a] += g[a];
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 3): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -331,17 +293,18 @@ impl WhiteboxTool for RadiusOfGyration {
         for row in 0..rows {
             for col in 0..columns {
                 z = input[(row, col)];
-                if z > 0f64 && z != nodata {
+                if z > 0f64 && !is_nodata(z, nodata) {
                     a = (z - min_val as f64) as usize;
                     output.set_value(row, col, gyradius[a]);
                 } else {
                     output.set_value(row, col, z);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 3 of 3): {}%", progress);
+                    reporter.progress("Progress (Loop 3 of 3)", progress);
                     old_progress = progress;
                 }
             }
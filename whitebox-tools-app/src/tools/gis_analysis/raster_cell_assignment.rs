@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to create a new raster with the same coordinates and dimensions
 /// (i.e. rows and columns) as an existing base image. Grid cells in the new raster will be
@@ -126,48 +125,12 @@ impl WhiteboxTool for RasterCellAssignment {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut what_to_assign = String::from("column");
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-a" || vec[0].to_lowercase() == "--assign" {
-                if keyval {
-                    what_to_assign = vec[1].to_string();
-                } else {
-                    what_to_assign = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let what_to_assign = parsed.get_string("assign").unwrap_or(String::from("column"));
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -180,18 +143,12 @@ impl WhiteboxTool for RasterCellAssignment {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -204,25 +161,23 @@ impl WhiteboxTool for RasterCellAssignment {
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
 
         match what_to_assign.to_lowercase().as_ref() {
             "column" | "columns" | "col" => {
                 for tid in 0..num_procs {
                     let tx = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data: Vec<f64> = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 data[col as usize] = col as f64;
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -230,13 +185,15 @@ impl WhiteboxTool for RasterCellAssignment {
             "row" | "rows" => {
                 for tid in 0..num_procs {
                     let tx = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data: Vec<f64> = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 data[col as usize] = row as f64;
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -245,13 +202,15 @@ impl WhiteboxTool for RasterCellAssignment {
                 for tid in 0..num_procs {
                     let input = input.clone();
                     let tx = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data: Vec<f64> = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 data[col as usize] = input.get_x_from_column(col);
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -260,13 +219,15 @@ impl WhiteboxTool for RasterCellAssignment {
                 for tid in 0..num_procs {
                     let input = input.clone();
                     let tx = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data: Vec<f64> = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 data[col as usize] = input.get_y_from_row(row);
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -285,10 +246,11 @@ impl WhiteboxTool for RasterCellAssignment {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,288 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Shared polygon-mask engine used by ClipRasterToPolygon and ErasePolygonFromRaster.
+*/
+
+use whitebox_common::algorithms::point_in_poly;
+use whitebox_common::structures::{Array2D, Point2D};
+use crate::tools::ProgressReporter;
+use whitebox_raster::*;
+use whitebox_vector::Shapefile;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+/// Controls how a polygon mask is rasterized and applied to a raster.
+pub(super) struct MaskOptions {
+    /// Keep the cells outside of the mask rather than those inside of it.
+    pub inverse: bool,
+    /// Distance, in map units, by which the mask is grown (positive) or shrunk (negative).
+    pub buffer: f64,
+    /// Compress the output extent to the bounding box of the mask. Ignored for inverse masks.
+    pub crop: bool,
+}
+
+/// Applies the mask formed by the polygon records `records` to `input`, returning a new raster
+/// that has not yet been written to `output_file`. Cells that are not retained by the mask are
+/// assigned the input NoData value.
+pub(super) fn apply_polygon_mask(
+    input: &Raster,
+    polygons: &Shapefile,
+    records: &[usize],
+    options: &MaskOptions,
+    output_file: &str,
+    verbose: bool,
+    reporter: &dyn ProgressReporter,
+) -> Result<Raster, Error> {
+    let res_x = input.configs.resolution_x;
+    let res_y = input.configs.resolution_y;
+    let nodata = input.configs.nodata;
+
+    // Determine the window of the input grid that the output will occupy.
+    let (mut row_start, mut row_end) = (0isize, input.configs.rows as isize - 1);
+    let (mut col_start, mut col_end) = (0isize, input.configs.columns as isize - 1);
+    if options.crop && !options.inverse {
+        let (mut x_min, mut x_max) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut y_min, mut y_max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for &record_num in records {
+            let record = polygons.get_record(record_num);
+            if record.num_points == 0 {
+                continue;
+            }
+            x_min = x_min.min(record.x_min);
+            x_max = x_max.max(record.x_max);
+            y_min = y_min.min(record.y_min);
+            y_max = y_max.max(record.y_max);
+        }
+        let grow = options.buffer.max(0f64);
+        row_start = row_start.max(input.get_row_from_y(y_max + grow));
+        row_end = row_end.min(input.get_row_from_y(y_min - grow));
+        col_start = col_start.max(input.get_column_from_x(x_min - grow));
+        col_end = col_end.min(input.get_column_from_x(x_max + grow));
+        if row_end < row_start || col_end < col_start {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The mask polygons do not overlap the input raster. This is may be due to a projection inconsistency between the input raster and the polygon file.",
+            ));
+        }
+    }
+
+    // The mask grid is padded by the buffer width so that polygons lying just beyond the window
+    // still contribute to a buffered mask.
+    let pad = (options.buffer.abs() / res_x.min(res_y)).ceil() as isize + 1;
+    let mask_row_start = row_start - pad;
+    let mask_col_start = col_start - pad;
+    let mask_rows = row_end - row_start + 1 + 2 * pad;
+    let mask_columns = col_end - col_start + 1 + 2 * pad;
+    if mask_rows > 500_000 || mask_columns > 500_000 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The output rasters dimensions are too big. This is may be due to a projection inconsistency between the input raster and the polygon file.",
+        ));
+    }
+    let mut mask: Array2D<u8> = Array2D::new(mask_rows, mask_columns, 0u8, 0u8)?;
+
+    let west = input.configs.west + (mask_col_start as f64) * res_x;
+    let north = input.configs.north - (mask_row_start as f64) * res_y;
+    let get_row = |y: f64| ((north - y) / res_y).floor() as isize;
+    let get_col = |x: f64| ((x - west) / res_x).floor() as isize;
+
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+    let num_records = records.len();
+    for (i, &record_num) in records.iter().enumerate() {
+        let record = polygons.get_record(record_num);
+        // Fill the outer parts first and then cut the holes back out.
+        for fill_holes in [false, true] {
+            for part in 0..record.num_parts as usize {
+                if record.is_hole(part as i32) != fill_holes {
+                    continue;
+                }
+                let start_point = record.parts[part] as usize;
+                let end_point = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize - 1
+                } else {
+                    record.num_points as usize - 1
+                };
+                let points = &record.points[start_point..end_point + 1];
+
+                let (mut starting_row, mut ending_row) = (mask_rows, 0isize);
+                let (mut starting_col, mut ending_col) = (mask_columns, 0isize);
+                for p in points {
+                    starting_row = starting_row.min(get_row(p.y));
+                    ending_row = ending_row.max(get_row(p.y));
+                    starting_col = starting_col.min(get_col(p.x));
+                    ending_col = ending_col.max(get_col(p.x));
+                }
+                starting_row = starting_row.max(0);
+                ending_row = ending_row.min(mask_rows - 1);
+                starting_col = starting_col.max(0);
+                ending_col = ending_col.min(mask_columns - 1);
+
+                let value = if fill_holes { 0u8 } else { 1u8 };
+                for r in starting_row..=ending_row {
+                    let y = north - res_y / 2f64 - r as f64 * res_y;
+                    for c in starting_col..=ending_col {
+                        let x = west + res_x / 2f64 + c as f64 * res_x;
+                        if point_in_poly(&Point2D::new(x, y), points) {
+                            mask.set_value(r, c, value);
+                        }
+                    }
+                }
+            }
+        }
+        reporter.check_cancelled()?;
+        if verbose && num_records > 1 {
+            progress = (100.0_f64 * (i + 1) as f64 / num_records as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Rasterizing mask", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    if options.buffer != 0f64 {
+        if verbose {
+            println!("Buffering mask...");
+        }
+        // A positive buffer adds background cells near the mask; a negative buffer removes mask
+        // cells near the background.
+        let target = if options.buffer > 0f64 { 1u8 } else { 0u8 };
+        let dist = distance_to_value(&mask, target, res_x, res_y)?;
+        let buffer = options.buffer.abs();
+        for r in 0..mask_rows {
+            for c in 0..mask_columns {
+                if mask.get_value(r, c) != target && dist.get_value(r, c) <= buffer {
+                    mask.set_value(r, c, target);
+                }
+            }
+        }
+    }
+
+    let mut configs = input.configs.clone();
+    configs.rows = (row_end - row_start + 1) as usize;
+    configs.columns = (col_end - col_start + 1) as usize;
+    configs.north = input.configs.north - row_start as f64 * res_y;
+    configs.south = configs.north - configs.rows as f64 * res_y;
+    configs.west = input.configs.west + col_start as f64 * res_x;
+    configs.east = configs.west + configs.columns as f64 * res_x;
+    let mut output = Raster::initialize_using_config(output_file, &configs);
+
+    let keep = if options.inverse { 0u8 } else { 1u8 };
+    for row in 0..configs.rows as isize {
+        for col in 0..configs.columns as isize {
+            if mask.get_value(row + pad, col + pad) == keep {
+                output.set_value(row, col, input.get_value(row + row_start, col + col_start));
+            } else {
+                output.set_value(row, col, nodata);
+            }
+        }
+        reporter.check_cancelled()?;
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (configs.rows - 1).max(1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Applying mask", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Returns the output file name used for the `n`th feature when splitting by feature, e.g.
+/// `clipped.tif` becomes `clipped_3.tif`. `n` may also be another label, such as a class value.
+pub(super) fn feature_file_name(output_file: &str, n: impl fmt::Display) -> String {
+    let file_path = Path::new(output_file);
+    match (file_path.file_stem(), file_path.extension()) {
+        (Some(stem), Some(ext)) => file_path
+            .with_file_name(format!(
+                "{}_{}.{}",
+                stem.to_string_lossy(),
+                n,
+                ext.to_string_lossy()
+            ))
+            .to_string_lossy()
+            .to_string(),
+        _ => format!("{}_{}", output_file, n),
+    }
+}
+
+/// Two-pass (8SSEDT) Euclidean distance from each cell to the nearest cell containing `target`.
+fn distance_to_value(
+    mask: &Array2D<u8>,
+    target: u8,
+    res_x: f64,
+    res_y: f64,
+) -> Result<Array2D<f64>, Error> {
+    let rows = mask.rows;
+    let columns = mask.columns;
+    let far = isize::MAX / 4;
+    // offsets from each cell to its nearest target cell, in cells
+    let mut dx: Array2D<isize> = Array2D::new(rows, columns, far, far)?;
+    let mut dy: Array2D<isize> = Array2D::new(rows, columns, far, far)?;
+    for r in 0..rows {
+        for c in 0..columns {
+            if mask.get_value(r, c) == target {
+                dx.set_value(r, c, 0);
+                dy.set_value(r, c, 0);
+            }
+        }
+    }
+
+    let sq_dist = |ox: isize, oy: isize| -> f64 {
+        if ox >= far || oy >= far {
+            f64::INFINITY
+        } else {
+            (ox as f64 * res_x).powi(2) + (oy as f64 * res_y).powi(2)
+        }
+    };
+    let relax = |dx: &mut Array2D<isize>, dy: &mut Array2D<isize>, r: isize, c: isize, nr: isize, nc: isize| {
+        if nr < 0 || nr >= rows || nc < 0 || nc >= columns {
+            return;
+        }
+        let (nx, ny) = (dx.get_value(nr, nc), dy.get_value(nr, nc));
+        if nx >= far {
+            return;
+        }
+        let (ox, oy) = (nx + (c - nc).abs(), ny + (r - nr).abs());
+        if sq_dist(ox, oy) < sq_dist(dx.get_value(r, c), dy.get_value(r, c)) {
+            dx.set_value(r, c, ox);
+            dy.set_value(r, c, oy);
+        }
+    };
+
+    for r in 0..rows {
+        for c in 0..columns {
+            for (nr, nc) in [(r - 1, c - 1), (r - 1, c), (r - 1, c + 1), (r, c - 1)] {
+                relax(&mut dx, &mut dy, r, c, nr, nc);
+            }
+        }
+        for c in (0..columns).rev() {
+            relax(&mut dx, &mut dy, r, c, r, c + 1);
+        }
+    }
+    for r in (0..rows).rev() {
+        for c in (0..columns).rev() {
+            for (nr, nc) in [(r + 1, c + 1), (r + 1, c), (r + 1, c - 1), (r, c + 1)] {
+                relax(&mut dx, &mut dy, r, c, nr, nc);
+            }
+        }
+        for c in 0..columns {
+            relax(&mut dx, &mut dy, r, c, r, c - 1);
+        }
+    }
+
+    let mut dist: Array2D<f64> = Array2D::new(rows, columns, f64::INFINITY, f64::INFINITY)?;
+    for r in 0..rows {
+        for c in 0..columns {
+            dist.set_value(r, c, sq_dist(dx.get_value(r, c), dy.get_value(r, c)).sqrt());
+        }
+    }
+    Ok(dist)
+}
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to measure the length of the perimeter of polygon features in a raster layer. The user must
 /// specify the name of the input raster file (`--input`) and optionally an output raster (`--output`), which is the
@@ -85,7 +84,7 @@ impl RasterPerimeter {
                 "grid cells".to_owned(),
                 "map units".to_owned(),
             ]),
-            default_value: Some("grid cells".to_owned()),
+            default_value: Some("map units".to_owned()),
             optional: true,
         });
 
@@ -161,58 +160,22 @@ impl WhiteboxTool for RasterPerimeter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
         let mut output_file = String::new();
         let mut output_raster = false;
-        let mut zero_back = false;
+        let zero_back = parsed.get_bool("zero_back");
         let mut is_grid_cell_units = false;
-        let mut output_text = false;
+        let output_text = parsed.get_bool("out_text");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_string("output") {
+            output_file = value.to_string();
+            output_raster = true;
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                output_raster = true;
-            } else if flag_val == "-units" {
-                is_grid_cell_units = if keyval {
-                    vec[1].to_string().to_lowercase().contains("cells")
-                } else {
-                    args[i + 1].to_string().to_lowercase().contains("cells")
-                };
-            } else if flag_val == "-zero_back" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    zero_back = true;
-                }
-            } else if flag_val == "-out_text" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    output_text = true;
-                }
-            }
+        if let Some(value) = parsed.get_string("units") {
+            is_grid_cell_units = value.to_string().to_lowercase().contains("cells");
         }
 
         if verbose {
@@ -226,14 +189,10 @@ impl WhiteboxTool for RasterPerimeter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
         if !output_raster && !output_text {
             println!("Warning: Niether a raster nor text outputs were selected. An area raster will be generated.");
@@ -242,9 +201,7 @@ impl WhiteboxTool for RasterPerimeter {
         }
 
         if output_raster {
-            if !output_file.contains(&sep) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
         }
 
         if verbose {
@@ -535,17 +492,15 @@ impl WhiteboxTool for RasterPerimeter {
             println!("Warning: the input file does not appear to be in a projected coordinate system. Perimeter values will only be estimates.");
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut resx = input.configs.resolution_x;
                 let mut resy = input.configs.resolution_y;
                 let mut data = vec![0f64; num_bins];
@@ -556,6 +511,9 @@ impl WhiteboxTool for RasterPerimeter {
                 let mut mid_lat: f64;
                 let mut res: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     if is_geographic {
                         mid_lat = input.get_y_from_row(row).to_radians();
                         resx = resx * 111_111.0 * mid_lat.cos();
@@ -564,7 +522,7 @@ impl WhiteboxTool for RasterPerimeter {
                     res = (resx + resy) / 2f64;
                     for col in 0..columns {
                         val = input.get_value(row, col);
-                        if val != nodata && val != back_val && val >= min_val && val <= max_val {
+                        if !is_nodata(val, nodata) && val != back_val && val >= min_val && val <= max_val {
                             bin = (val - min_val).floor() as usize;
                             val2 = 0;
                             for n in 0..8 {
@@ -582,7 +540,9 @@ impl WhiteboxTool for RasterPerimeter {
                         }
                     }
                 }
-                tx.send(data).unwrap();
+                if tx.send(data).is_err() {
+                    return;
+                }
             });
         }
 
@@ -593,10 +553,11 @@ impl WhiteboxTool for RasterPerimeter {
                 data[a] += data_rx[a] * avg_res;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (tid + 1) as f64 / num_procs as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -614,15 +575,16 @@ impl WhiteboxTool for RasterPerimeter {
             for row in 0..rows {
                 for col in 0..columns {
                     val = input.get_value(row, col);
-                    if val != nodata && val != back_val && val >= min_val && val <= max_val {
+                    if !is_nodata(val, nodata) && val != back_val && val >= min_val && val <= max_val {
                         bin = (val - min_val).floor() as usize;
                         output.set_value(row, col, data[bin] as f64);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Outputting raster: {}%", progress);
+                        reporter.progress("Outputting raster", progress);
                         old_progress = progress;
                     }
                 }
@@ -8,7 +8,6 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::collections::HashMap;
 use std::env;
 use std::f64;
@@ -16,7 +15,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool creates a new raster in which the value of each grid cell is determined by an input raster (`--input`) and a
 /// collection of user-defined classes. The user must specify the *New* value, the *From* value, and the *To Just Less Than*
@@ -153,55 +152,13 @@ impl WhiteboxTool for Reclass {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut reclass_str = String::new();
-        let mut assign_mode = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if vec[0].to_lowercase() == "-reclass_vals"
-                || vec[0].to_lowercase() == "--reclass_vals"
-            {
-                reclass_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if vec[0].to_lowercase() == "-assign_mode"
-                || vec[0].to_lowercase() == "--assign_mode"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    assign_mode = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let reclass_str = parsed.get_string("reclass_vals").unwrap_or_default();
+        let assign_mode = parsed.get_bool("assign_mode");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -214,17 +171,11 @@ impl WhiteboxTool for Reclass {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -274,12 +225,8 @@ impl WhiteboxTool for Reclass {
         };
         let reclass_vals = Arc::new(reclass_vals);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
@@ -289,14 +236,14 @@ impl WhiteboxTool for Reclass {
                 let input = input.clone();
                 let reclass_vals = reclass_vals.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z: f64;
                     let mut prev_idx: usize = 0;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 // This is a shortcut intended to take advantage of the inherent
                                 // spatial autocorrelation in spatial distributions to speed up
                                 // the search for the appropriate range bin.
@@ -318,7 +265,9 @@ impl WhiteboxTool for Reclass {
                                 data[col as usize] = z;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -327,10 +276,11 @@ impl WhiteboxTool for Reclass {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(row, data);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -357,13 +307,13 @@ impl WhiteboxTool for Reclass {
                 let input = input.clone();
                 let assign_map = assign_map.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 // is z in the hashmap?
                                 if assign_map.contains_key(&((z * multiplier).round() as i64)) {
                                     z = *assign_map
@@ -373,7 +323,9 @@ impl WhiteboxTool for Reclass {
                                 data[col as usize] = z;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -382,10 +334,11 @@ impl WhiteboxTool for Reclass {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(row, data);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tools calculates a type of shape complexity index for raster objects. The index is equal to the average
 /// number of intersections of the group of vertical and horizontal transects passing through an object. Simple
@@ -121,40 +120,11 @@ impl WhiteboxTool for ShapeComplexityIndexRaster {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -167,18 +137,12 @@ impl WhiteboxTool for ShapeComplexityIndexRaster {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...");
@@ -196,17 +160,15 @@ impl WhiteboxTool for ShapeComplexityIndexRaster {
         let range = max_val - min_val + 0.00001f64; // otherwise the max value is outside the range
         let num_bins = range.ceil() as usize;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut freq_data = vec![0usize; num_bins];
                 let mut min_row = vec![isize::max_value(); num_bins];
                 let mut max_row = vec![isize::min_value(); num_bins];
@@ -216,6 +178,9 @@ impl WhiteboxTool for ShapeComplexityIndexRaster {
                 let mut n1: f64;
                 let mut bin: usize;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     for col in 0..columns {
                         val = input.get_value(row, col);
                         if val > 0f64 && val >= min_val && val <= max_val {
@@ -258,8 +223,9 @@ impl WhiteboxTool for ShapeComplexityIndexRaster {
                     }
                 }
 
-                tx.send((freq_data, min_row, max_row, min_col, max_col))
-                    .unwrap();
+                if tx.send((freq_data, min_row, max_row, min_col, max_col)).is_err() {
+                    return;
+                }
             });
         }
 
@@ -287,10 +253,11 @@ impl WhiteboxTool for ShapeComplexityIndexRaster {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (tid + 1) as f64 / num_procs as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -324,10 +291,11 @@ impl WhiteboxTool for ShapeComplexityIndexRaster {
                     output.set_value(row, col, 0f64);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
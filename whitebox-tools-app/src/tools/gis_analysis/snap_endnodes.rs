@@ -139,49 +139,18 @@ impl WhiteboxTool for SnapEndnodes {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
         let mut precision = std::f64::EPSILON;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-snap" {
-                precision = if keyval {
-                    vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                };
-                if precision == 0f64 {
-                    precision = std::f64::EPSILON;
-                }
+        if let Some(value) = parsed.get_f64("snap") {
+            precision = value;
+            if precision == 0f64 {
+                precision = std::f64::EPSILON;
             }
         }
 
@@ -202,13 +171,9 @@ impl WhiteboxTool for SnapEndnodes {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Shapefile::read(&input_file)?;
 
@@ -227,6 +192,7 @@ impl WhiteboxTool for SnapEndnodes {
         let mut last_point_in_part: usize;
         let mut polylines: Vec<Polyline> = vec![];
         for record_num in 0..input.num_records {
+            reporter.check_cancelled()?;
             let record = input.get_record(record_num);
             if record.shape_type != ShapeType::Null {
                 for part in 0..record.num_parts as usize {
@@ -0,0 +1,676 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::structures::{
+    DistanceMetric, FixedRadiusSearch2D, LineSegment, Point2D, RectangleWithData,
+};
+use crate::tools::*;
+use whitebox_vector::*;
+use rstar::{RTree, AABB};
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool can be used to snap the vertices of the features in an input vector (`--input`) to
+/// those of a reference vector (`--reference`), such that features that are within a snap distance
+/// (`--tolerance`, in map units) of one another become coincident. It is commonly used to clean
+/// digitized hydrography against DEM-derived stream networks, or to integrate a layer with itself,
+/// prior to carrying out network analysis. If no reference vector is specified, the input vector
+/// is snapped to itself, i.e. clusters of vertices that are within the tolerance distance of one
+/// another are collapsed to a single location.
+///
+/// Snapping proceeds in up to three stages. First, each vertex in the input is moved to the nearest
+/// reference vertex within the tolerance distance. Second, unless the `--vertices_only` flag is
+/// specified, input vertices that were not snapped to a vertex are moved onto the nearest reference
+/// edge within the tolerance distance. Lastly, if the `--insert_nodes` flag is specified and the input
+/// is of a line or polygon ShapeType, new vertices are inserted into the input features wherever they
+/// cross a reference edge, and wherever a reference vertex lies within the tolerance distance of an
+/// input edge. This final stage ensures that the output will contain nodes at each of the junctions
+/// with the reference network.
+///
+/// Vertices that become coincident with their neighbours are removed, as are any line parts with fewer
+/// than two vertices and polygon parts with fewer than four vertices after snapping. Features that have
+/// no remaining parts are excluded from the output. The attributes of the input vector are copied
+/// to the output. Measure and z values are retained and are linearly interpolated for any inserted
+/// vertices.
+///
+/// The tolerance should be set to a value that is larger than the typical misalignment between
+/// the two layers but smaller than the spacing between features that should remain distinct. Large
+/// tolerances can collapse small features.
+///
+/// # See Also
+/// `CleanVector`, `MergeLineSegments`, `ExtendVectorLines`
+pub struct SnapVectors {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SnapVectors {
+    pub fn new() -> SnapVectors {
+        // public constructor
+        let name = "SnapVectors".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Snaps the vertices and edges of a vector to those of a reference vector, or to itself, within a tolerance."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reference Vector File (optional)".to_owned(),
+            flags: vec!["--reference".to_owned()],
+            description: "Input reference vector file. If unspecified, the input is snapped to itself."
+                .to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Vector File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Snap Tolerance".to_owned(),
+            flags: vec!["--snap".to_owned(), "--tolerance".to_owned()],
+            description: "Snap tolerance, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Snap to vertices only?".to_owned(),
+            flags: vec!["--vertices_only".to_owned()],
+            description: "Only snap to reference vertices and not to reference edges.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Insert nodes at crossings?".to_owned(),
+            flags: vec!["--insert_nodes".to_owned()],
+            description: "Insert vertices where input edges cross, or pass within the tolerance of, the reference features.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=digitized_streams.shp --reference=dem_streams.shp -o=snapped.shp --tolerance=5.0 --insert_nodes",
+            short_exe, name
+        ).replace("*", &sep);
+
+        SnapVectors {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SnapVectors {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut reference_file = parsed.get_string("reference").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let tolerance = parsed.get_f64("tolerance").unwrap_or(1.0f64);
+        let vertices_only = parsed.get_bool("vertices_only");
+        let insert_nodes = parsed.get_bool("insert_nodes");
+
+        // read the arguments
+
+        if tolerance <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The snap tolerance must be greater than zero.",
+            ));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        let self_snap = reference_file.trim().is_empty();
+        if !self_snap {
+            reference_file = resolve_input_path(&reference_file, working_directory)?;
+        }
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+        let shape_type = input.header.shape_type;
+        let base_type = shape_type.base_shape_type();
+        let is_lines_or_polygons =
+            base_type == ShapeType::PolyLine || base_type == ShapeType::Polygon;
+        let min_vertices = if base_type == ShapeType::Polygon {
+            4
+        } else if base_type == ShapeType::PolyLine {
+            2
+        } else {
+            1
+        };
+
+        let mut parts = get_parts(&input);
+
+        // Gather the reference vertices and edges. When snapping the input to itself,
+        // the reference features are derived from the input as it is being modified.
+        let mut ref_vertices: Vec<Point2D> = vec![];
+        let mut ref_segments: Vec<(LineSegment, usize)> = vec![];
+        if !self_snap {
+            let reference = Shapefile::read(&reference_file)?;
+            let ref_is_lines_or_polygons = reference.header.shape_type.base_shape_type()
+                == ShapeType::PolyLine
+                || reference.header.shape_type.base_shape_type() == ShapeType::Polygon;
+            for part in get_parts(&reference) {
+                ref_vertices.extend_from_slice(&part.points);
+                if ref_is_lines_or_polygons {
+                    for i in 0..part.points.len().saturating_sub(1) {
+                        ref_segments.push((
+                            LineSegment::new(part.points[i], part.points[i + 1]),
+                            std::usize::MAX,
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Stage 1: snap vertices to reference vertices.
+        let tolerance_sqrd = tolerance * tolerance;
+        let mut snapped: Vec<Vec<bool>> = parts
+            .iter()
+            .map(|part| vec![false; part.points.len()])
+            .collect();
+        let mut frs: FixedRadiusSearch2D<usize> =
+            FixedRadiusSearch2D::new(tolerance, DistanceMetric::SquaredEuclidean);
+        for i in 0..ref_vertices.len() {
+            frs.insert(ref_vertices[i].x, ref_vertices[i].y, i);
+        }
+        let mut num_snapped = 0usize;
+        let mut p: Point2D;
+        for part_num in 0..parts.len() {
+            for i in 0..parts[part_num].points.len() {
+                p = parts[part_num].points[i];
+                let ret = frs.search(p.x, p.y);
+                let mut min_dist = f64::INFINITY;
+                let mut nearest = std::usize::MAX;
+                for (index, dist) in ret {
+                    if dist <= tolerance_sqrd && dist < min_dist {
+                        min_dist = dist;
+                        nearest = index;
+                    }
+                }
+                if nearest != std::usize::MAX {
+                    if ref_vertices[nearest] != p {
+                        parts[part_num].points[i] = ref_vertices[nearest];
+                        num_snapped += 1;
+                    }
+                    snapped[part_num][i] = true;
+                } else if self_snap {
+                    // This vertex becomes the snap target for any nearby vertices that follow.
+                    frs.insert(p.x, p.y, ref_vertices.len());
+                    ref_vertices.push(p);
+                    snapped[part_num][i] = true;
+                }
+            }
+
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * (part_num + 1) as f64 / parts.len() as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Snapping vertices", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Stage 2: snap the remaining vertices to reference edges.
+        if self_snap && is_lines_or_polygons {
+            ref_segments = get_segments(&parts);
+        }
+        if !vertices_only && !ref_segments.is_empty() {
+            let tree = build_segment_tree(&ref_segments, tolerance);
+            for part_num in 0..parts.len() {
+                for i in 0..parts[part_num].points.len() {
+                    p = parts[part_num].points[i];
+                    if self_snap {
+                        // Vertices within a self-snapped layer are only moved onto the edges
+                        // of other parts; they have already been collapsed onto one another.
+                        snapped[part_num][i] = false;
+                    }
+                    if snapped[part_num][i] {
+                        continue;
+                    }
+                    let envelope = AABB::from_corners(
+                        [p.x - tolerance, p.y - tolerance],
+                        [p.x + tolerance, p.y + tolerance],
+                    );
+                    let mut min_dist = f64::INFINITY;
+                    let mut nearest_point = p;
+                    for rect in tree.locate_in_envelope_intersecting(&envelope) {
+                        let (segment, source_part) = ref_segments[rect.data];
+                        if self_snap && source_part == part_num {
+                            continue;
+                        }
+                        let (q, _) = segment.nearest_point_on_segment(p);
+                        let dist = q.distance_squared(&p);
+                        if dist <= tolerance_sqrd && dist < min_dist {
+                            min_dist = dist;
+                            nearest_point = q;
+                        }
+                    }
+                    if min_dist < f64::INFINITY && nearest_point != p {
+                        parts[part_num].points[i] = nearest_point;
+                        snapped[part_num][i] = true;
+                        num_snapped += 1;
+                    }
+                }
+
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * (part_num + 1) as f64 / parts.len() as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Snapping to edges", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        }
+
+        // Stage 3: insert nodes at crossings with the reference features.
+        let mut num_inserted = 0usize;
+        if insert_nodes && is_lines_or_polygons {
+            if self_snap {
+                ref_segments = get_segments(&parts);
+            }
+            if !ref_segments.is_empty() {
+                let tree = build_segment_tree(&ref_segments, tolerance);
+                for part_num in 0..parts.len() {
+                    let part = &parts[part_num];
+                    let num_points = part.points.len();
+                    if num_points < 2 {
+                        continue;
+                    }
+                    let mut new_part = SnapPart::new(part.record, part.has_m, part.has_z);
+                    for i in 0..num_points - 1 {
+                        new_part.push_from(part, i);
+                        let segment = LineSegment::new(part.points[i], part.points[i + 1]);
+                        let bb = segment.get_bounding_box();
+                        let envelope = AABB::from_corners(
+                            [bb.min_x - tolerance, bb.min_y - tolerance],
+                            [bb.max_x + tolerance, bb.max_y + tolerance],
+                        );
+                        let mut nodes: Vec<(f64, Point2D)> = vec![];
+                        for rect in tree.locate_in_envelope_intersecting(&envelope) {
+                            let (other, source_part) = ref_segments[rect.data];
+                            if self_snap && source_part == part_num {
+                                continue;
+                            }
+                            if let Some(ls) = segment.get_intersection(&other) {
+                                nodes.push((segment.nearest_point_on_segment(ls.p1).1, ls.p1));
+                                if ls.p1 != ls.p2 {
+                                    nodes.push((segment.nearest_point_on_segment(ls.p2).1, ls.p2));
+                                }
+                            }
+                            for q in [other.p1, other.p2].iter() {
+                                let (_, t) = segment.nearest_point_on_segment(*q);
+                                if segment.dist_to_segment_squared(*q) <= tolerance_sqrd {
+                                    nodes.push((t, *q));
+                                }
+                            }
+                        }
+                        nodes.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                        let mut last_node = part.points[i];
+                        for (t, q) in nodes {
+                            if t <= 0f64
+                                || t >= 1f64
+                                || q.nearly_equals(&last_node)
+                                || q.nearly_equals(&part.points[i + 1])
+                            {
+                                continue;
+                            }
+                            new_part.push_interpolated(part, i, t, q);
+                            last_node = q;
+                            num_inserted += 1;
+                        }
+                    }
+                    new_part.push_from(part, num_points - 1);
+                    parts[part_num] = new_part;
+
+                    reporter.check_cancelled()?;
+                    if verbose {
+                        progress =
+                            (100.0_f64 * (part_num + 1) as f64 / parts.len() as f64) as usize;
+                        if progress != old_progress {
+                            reporter.progress("Inserting nodes", progress);
+                            old_progress = progress;
+                        }
+                    }
+                }
+            }
+        }
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, shape_type, true)?;
+
+        let mut num_removed = 0usize;
+        let mut part_num = 0usize;
+        for record_num in 0..input.num_records {
+            reporter.check_cancelled()?;
+            let mut geometry = ShapefileGeometry::new(shape_type);
+            while part_num < parts.len() && parts[part_num].record == record_num {
+                let part = parts[part_num].remove_duplicate_vertices(base_type);
+                part_num += 1;
+                if part.points.len() < min_vertices {
+                    continue;
+                }
+                match shape_type.base_shape_type() {
+                    ShapeType::Point => {
+                        if part.has_z {
+                            geometry.add_pointz(part.points[0], part.measures[0], part.z_values[0]);
+                        } else if part.has_m {
+                            geometry.add_pointm(part.points[0], part.measures[0]);
+                        } else {
+                            geometry.add_point(part.points[0]);
+                        }
+                    }
+                    ShapeType::MultiPoint => {
+                        for i in 0..part.points.len() {
+                            if part.has_z {
+                                geometry.add_pointz(part.points[i], part.measures[i], part.z_values[i]);
+                            } else if part.has_m {
+                                geometry.add_pointm(part.points[i], part.measures[i]);
+                            } else {
+                                geometry.add_point(part.points[i]);
+                            }
+                        }
+                    }
+                    _ => {
+                        if part.has_z {
+                            geometry.add_partz(&part.points, &part.measures, &part.z_values);
+                        } else if part.has_m {
+                            geometry.add_partm(&part.points, &part.measures);
+                        } else {
+                            geometry.add_part(&part.points);
+                        }
+                    }
+                }
+            }
+            if geometry.num_points > 0 {
+                output.add_record(geometry);
+                output
+                    .attributes
+                    .add_record(input.attributes.get_record(record_num), false);
+            } else if input.get_record(record_num).shape_type != ShapeType::Null {
+                num_removed += 1;
+            }
+        }
+
+        if verbose {
+            println!("Number of snapped vertices: {}", num_snapped);
+            if insert_nodes {
+                println!("Number of inserted nodes: {}", num_inserted);
+            }
+            if num_removed > 0 {
+                println!(
+                    "Warning: {} features collapsed during snapping and were removed.",
+                    num_removed
+                );
+            }
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+/// A single part of a feature, along with its measure and z data.
+struct SnapPart {
+    record: usize,
+    points: Vec<Point2D>,
+    measures: Vec<f64>,
+    z_values: Vec<f64>,
+    has_m: bool,
+    has_z: bool,
+}
+
+impl SnapPart {
+    fn new(record: usize, has_m: bool, has_z: bool) -> SnapPart {
+        SnapPart {
+            record: record,
+            points: vec![],
+            measures: vec![],
+            z_values: vec![],
+            has_m: has_m,
+            has_z: has_z,
+        }
+    }
+
+    /// Copies the vertex at index i of another part into this part.
+    fn push_from(&mut self, other: &SnapPart, i: usize) {
+        self.points.push(other.points[i]);
+        if self.has_m || self.has_z {
+            self.measures.push(other.measures[i]);
+        }
+        if self.has_z {
+            self.z_values.push(other.z_values[i]);
+        }
+    }
+
+    /// Adds the point p, located a proportion t along the segment starting at
+    /// vertex i of another part, interpolating the measure and z values.
+    fn push_interpolated(&mut self, other: &SnapPart, i: usize, t: f64, p: Point2D) {
+        self.points.push(p);
+        if self.has_m || self.has_z {
+            self.measures
+                .push(other.measures[i] + t * (other.measures[i + 1] - other.measures[i]));
+        }
+        if self.has_z {
+            self.z_values
+                .push(other.z_values[i] + t * (other.z_values[i + 1] - other.z_values[i]));
+        }
+    }
+
+    fn remove_duplicate_vertices(&self, base_type: ShapeType) -> SnapPart {
+        let mut ret = SnapPart::new(self.record, self.has_m, self.has_z);
+        if base_type == ShapeType::Point || base_type == ShapeType::MultiPoint {
+            for i in 0..self.points.len() {
+                ret.push_from(self, i);
+            }
+            return ret;
+        }
+        for i in 0..self.points.len() {
+            if i == 0 || !self.points[i].nearly_equals(&self.points[i - 1]) {
+                ret.push_from(self, i);
+            }
+        }
+        ret
+    }
+}
+
+fn get_parts(vector: &Shapefile) -> Vec<SnapPart> {
+    let dimension = vector.header.shape_type.dimension();
+    let has_z = dimension == ShapeTypeDimension::Z;
+    let has_m = dimension == ShapeTypeDimension::Measure;
+    let mut parts: Vec<SnapPart> = vec![];
+    let (mut part_start, mut part_end): (usize, usize);
+    for record_num in 0..vector.num_records {
+        let record = vector.get_record(record_num);
+        if record.shape_type == ShapeType::Null || record.points.is_empty() {
+            continue;
+        }
+        // The measure array of a PointZ-type record is optional.
+        let record_has_m = record.m_array.len() == record.points.len();
+        let num_parts = record.num_parts.max(1) as usize;
+        for part in 0..num_parts {
+            part_start = if record.parts.is_empty() {
+                0
+            } else {
+                record.parts[part] as usize
+            };
+            part_end = if part < num_parts - 1 {
+                record.parts[part + 1] as usize - 1
+            } else {
+                record.num_points as usize - 1
+            };
+            let mut sp = SnapPart::new(record_num, has_m, has_z);
+            for i in part_start..=part_end {
+                sp.points.push(record.points[i]);
+                if has_m || has_z {
+                    sp.measures
+                        .push(if record_has_m { record.m_array[i] } else { 0f64 });
+                }
+                if has_z {
+                    sp.z_values.push(record.z_array[i]);
+                }
+            }
+            parts.push(sp);
+        }
+    }
+    parts
+}
+
+fn get_segments(parts: &[SnapPart]) -> Vec<(LineSegment, usize)> {
+    let mut segments = vec![];
+    for part_num in 0..parts.len() {
+        let points = &parts[part_num].points;
+        for i in 0..points.len().saturating_sub(1) {
+            segments.push((LineSegment::new(points[i], points[i + 1]), part_num));
+        }
+    }
+    segments
+}
+
+fn build_segment_tree(
+    segments: &[(LineSegment, usize)],
+    tolerance: f64,
+) -> RTree<RectangleWithData<usize>> {
+    let mut segment_aabb: Vec<RectangleWithData<usize>> = Vec::with_capacity(segments.len());
+    for i in 0..segments.len() {
+        let bb = segments[i].0.get_bounding_box();
+        segment_aabb.push(RectangleWithData::new(
+            i,
+            [bb.min_x - tolerance, bb.min_y - tolerance],
+            [bb.max_x + tolerance, bb.max_y + tolerance],
+        ));
+    }
+    RTree::bulk_load(segment_aabb)
+}
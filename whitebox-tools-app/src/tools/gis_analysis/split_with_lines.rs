@@ -153,50 +153,15 @@ impl WhiteboxTool for SplitWithLines {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input1_file: String = "".to_string();
-        let mut input2_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input1_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut input2_file: String = parsed.get_string("split").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input1_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-split" {
-                input2_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -213,17 +178,11 @@ impl WhiteboxTool for SplitWithLines {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input1_file.contains(path::MAIN_SEPARATOR) && !input1_file.contains("/") {
-            input1_file = format!("{}{}", working_directory, input1_file);
-        }
+        input1_file = resolve_input_path(&input1_file, working_directory)?;
 
-        if !input2_file.contains(path::MAIN_SEPARATOR) && !input2_file.contains("/") {
-            input2_file = format!("{}{}", working_directory, input2_file);
-        }
+        input2_file = resolve_input_path(&input2_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input1 = Shapefile::read(&input1_file)?;
         let projection = input1.projection.clone();
@@ -267,6 +226,7 @@ impl WhiteboxTool for SplitWithLines {
         let mut polylines1: Vec<Polyline> = Vec::with_capacity(input1.get_total_num_parts());
         let mut bb1: Vec<BoundingBox> = Vec::with_capacity(input1.get_total_num_parts());
         for record_num in 0..input1.num_records {
+            reporter.check_cancelled()?;
             let record = input1.get_record(record_num);
             for part in 0..record.num_parts as usize {
                 num_polys += 1;
@@ -293,6 +253,7 @@ impl WhiteboxTool for SplitWithLines {
         let mut polylines2: Vec<Polyline> = Vec::with_capacity(input2.get_total_num_parts());
         let mut bb2: Vec<BoundingBox> = Vec::with_capacity(input2.get_total_num_parts());
         for record_num in 0..input2.num_records {
+            reporter.check_cancelled()?;
             let record = input2.get_record(record_num);
             for part in 0..record.num_parts as usize {
                 first_point_in_part = record.parts[part] as usize;
@@ -542,11 +503,12 @@ impl WhiteboxTool for SplitWithLines {
                     output.attributes.add_record(atts, false);
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * (record_num1 + 1) as f64 / polylines1.len() as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -660,11 +622,12 @@ impl WhiteboxTool for SplitWithLines {
                     lengths.push(split_lines[j].length());
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * (record_num1 + 1) as f64 / polylines1.len() as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -700,10 +663,11 @@ impl WhiteboxTool for SplitWithLines {
                 p = polylines[i].last_vertex();
                 kdtree.add([p.x, p.y], last_node_id(i)).unwrap();
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                     if progress != old_progress {
-                        println!("Creating tree: {}%", progress);
+                        reporter.progress("Creating tree", progress);
                         old_progress = progress;
                     }
                 }
@@ -744,10 +708,11 @@ impl WhiteboxTool for SplitWithLines {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                     if progress != old_progress {
-                        println!("Finding node vertices: {}%", progress);
+                        reporter.progress("Finding node vertices", progress);
                         old_progress = progress;
                     }
                 }
@@ -896,10 +861,11 @@ impl WhiteboxTool for SplitWithLines {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                     if progress != old_progress {
-                        println!("Finding polygons: {}%", progress);
+                        reporter.progress("Finding polygons", progress);
                         old_progress = progress;
                     }
                 }
@@ -0,0 +1,334 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{standard_deviational_ellipse, weighted_mean_center};
+use whitebox_common::structures::Point2D;
+use super::point_groups::read_point_groups;
+use crate::tools::*;
+use whitebox_vector::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// The number of vertices used to represent each output ellipse.
+const NUM_VERTICES: usize = 72;
+
+/// This tool calculates the standard deviational ellipse of the points in an input vector points layer,
+/// which summarizes both the dispersion and the directional trend of a point distribution. For example,
+/// the ellipse may reveal that disease cases are elongated along a river valley or that crime incidents
+/// follow a transportation corridor. The ellipse is centred on the (weighted) mean center of the points
+/// and its axes are the principal axes of the (weighted) covariance matrix of the point coordinates,
+/// such that the major axis is aligned with the direction of maximum dispersion. The lengths of the
+/// semi-axes are the standard deviations of the points along each axis multiplied by `--num_std`
+/// (default 1.0).
+///
+/// Points may optionally be weighted using a numeric attribute field (`--weight_field`). Features
+/// with null or negative weights are ignored. If a case field (`--case_field`) is specified, a separate
+/// ellipse is calculated for each unique value of the field. Cases containing fewer than three points
+/// are not output.
+///
+/// The output is a vector polygon file containing one ellipse for each case. The attribute table
+/// contains the case field value (if specified), the number of points (N), the sum of the weights
+/// (SUM_WEIGHT), the coordinates of the mean center (CENTER_X and CENTER_Y), the standard deviations
+/// along the major and minor axes (MAJOR_SD and MINOR_SD), the rotation of the major axis in degrees
+/// clockwise from north (ROTATION), and the number of standard deviations used to size the ellipse
+/// (NUM_STD).
+///
+/// # See Also
+/// `MeanCenter`, `MedianCenter`, `StandardDistance`
+pub struct StandardDeviationalEllipse {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl StandardDeviationalEllipse {
+    pub fn new() -> StandardDeviationalEllipse {
+        // public constructor
+        let name = "StandardDeviationalEllipse".to_string();
+        let toolbox = "GIS Analysis".to_string();
+        let description =
+            "Calculates the standard deviational ellipse of a vector points layer, optionally by case."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Points File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector points file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector polygon file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Weight Field".to_owned(),
+            flags: vec!["--weight_field".to_owned()],
+            description: "Numeric field used to weight the points (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Case Field".to_owned(),
+            flags: vec!["--case_field".to_owned()],
+            description: "Field used to group the points into cases (optional).".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Standard Deviations".to_owned(),
+            flags: vec!["--num_std".to_owned()],
+            description: "Number of standard deviations used to size the output polygons.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=cases.shp -o=ellipses.shp --case_field=TYPE --weight_field=COUNT --case_field=YEAR",
+            short_exe, name
+        ).replace("*", &sep);
+
+        StandardDeviationalEllipse {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for StandardDeviationalEllipse {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let weight_field = parsed.get_string("weight_field").unwrap_or_default();
+        let case_field = parsed.get_string("case_field").unwrap_or_default();
+        let num_std = parsed.get_f64("num_std").unwrap_or(1f64);
+
+        // read the arguments
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if num_std <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of standard deviations must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+        let groups = read_point_groups(&input, &weight_field, &case_field, reporter)?;
+
+        // create output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, ShapeType::Polygon, false)?;
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        if !case_field.is_empty() {
+            let i = input.attributes.get_field_num(&case_field).unwrap();
+            output.attributes.add_field(input.attributes.get_field(i));
+        }
+        output
+            .attributes
+            .add_field(&AttributeField::new("N", FieldDataType::Int, 9u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("SUM_WEIGHT", FieldDataType::Real, 18u8, 4u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("CENTER_X", FieldDataType::Real, 18u8, 6u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("CENTER_Y", FieldDataType::Real, 18u8, 6u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("MAJOR_SD", FieldDataType::Real, 18u8, 6u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("MINOR_SD", FieldDataType::Real, 18u8, 6u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("ROTATION", FieldDataType::Real, 10u8, 4u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("NUM_STD", FieldDataType::Real, 6u8, 2u8));
+
+        let mut fid = 1i32;
+        for group in &groups {
+            reporter.check_cancelled()?;
+            let sum_weight: f64 = group.weights.iter().sum();
+            if sum_weight <= 0f64 || group.points.len() < 3 {
+                continue;
+            }
+            let center = weighted_mean_center(&group.points, &group.weights);
+            let (major_sd, minor_sd, rotation) =
+                standard_deviational_ellipse(&group.points, &group.weights, &center);
+            let a = major_sd * num_std;
+            let b = minor_sd * num_std;
+            // unit vectors along the major and minor axes
+            let (ux, uy) = (rotation.sin(), rotation.cos());
+            let (vx, vy) = (rotation.cos(), -rotation.sin());
+
+            // increasing t traces the ellipse in clockwise order, as required for polygon hulls
+            let mut vertices: Vec<Point2D> = Vec::with_capacity(NUM_VERTICES + 1);
+            for k in 0..NUM_VERTICES {
+                let t = 2f64 * std::f64::consts::PI * k as f64 / NUM_VERTICES as f64;
+                let (ca, sb) = (a * t.cos(), b * t.sin());
+                vertices.push(Point2D::new(
+                    center.x + ca * ux + sb * vx,
+                    center.y + ca * uy + sb * vy,
+                ));
+            }
+            vertices.push(vertices[0]);
+            let mut sfg = ShapefileGeometry::new(ShapeType::Polygon);
+            sfg.add_part(&vertices);
+            output.add_record(sfg);
+            let mut atts = vec![FieldData::Int(fid)];
+            if !case_field.is_empty() {
+                atts.push(group.case_value.clone());
+            }
+            atts.push(FieldData::Int(group.points.len() as i32));
+            atts.push(FieldData::Real(sum_weight));
+            atts.push(FieldData::Real(center.x));
+            atts.push(FieldData::Real(center.y));
+            atts.push(FieldData::Real(major_sd));
+            atts.push(FieldData::Real(minor_sd));
+            atts.push(FieldData::Real(rotation.to_degrees()));
+            atts.push(FieldData::Real(num_std));
+            output.attributes.add_record(atts, false);
+            fid += 1;
+        }
+
+        if output.num_records == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input file does not contain any cases with enough points with positive weights.",
+            ));
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
@@ -177,66 +177,22 @@ impl WhiteboxTool for SymmetricalDifference {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut overlay_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut overlay_file = parsed.get_string("overlay").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut precision = std::f64::EPSILON;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val.contains("-input") {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-overlay" {
-                overlay_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-snap" {
-                precision = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if precision == 0f64 {
-                    precision = std::f64::EPSILON;
-                }
+        if let Some(value) = parsed.get_f64("snap") {
+            precision = value;
+            if precision == 0f64 {
+                precision = std::f64::EPSILON;
             }
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -253,15 +209,9 @@ impl WhiteboxTool for SymmetricalDifference {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !overlay_file.contains(&sep) && !overlay_file.contains("/") {
-            overlay_file = format!("{}{}", working_directory, overlay_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        overlay_file = resolve_input_path(&overlay_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -326,11 +276,13 @@ impl WhiteboxTool for SymmetricalDifference {
                 let mut tree = KdTree::with_capacity(dimensions, capacity_per_node);
                 let mut p: Point2D;
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     p = record.points[0];
                     tree.add([p.x, p.y], 1).unwrap();
                 }
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     p = record.points[0];
                     tree.add([p.x, p.y], 2).unwrap();
@@ -368,10 +320,11 @@ impl WhiteboxTool for SymmetricalDifference {
                         }
                         output.attributes.add_record(out_atts, false);
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * record_num as f64 / num_total_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -405,11 +358,12 @@ impl WhiteboxTool for SymmetricalDifference {
                         }
                         output.attributes.add_record(out_atts, false);
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (record_num + input.num_records) as f64
                             / num_total_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -423,6 +377,7 @@ impl WhiteboxTool for SymmetricalDifference {
                 let mut p: Point2D;
                 let mut total_points = 0;
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for p in &record.points {
                         tree.add([p.x, p.y], 1).unwrap();
@@ -431,6 +386,7 @@ impl WhiteboxTool for SymmetricalDifference {
                 }
                 let num_points_input = total_points;
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     for p in &record.points {
                         tree.add([p.x, p.y], 2).unwrap();
@@ -458,10 +414,11 @@ impl WhiteboxTool for SymmetricalDifference {
                                 break;
                             }
                         }
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * i as f64 / num_total_points) as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -483,11 +440,12 @@ impl WhiteboxTool for SymmetricalDifference {
                                 break;
                             }
                         }
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (i + num_points_input) as f64
                                 / num_total_points) as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -507,6 +465,7 @@ impl WhiteboxTool for SymmetricalDifference {
                     match input.header.shape_type.dimension() {
                         ShapeTypeDimension::XY => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i] {
@@ -516,6 +475,7 @@ impl WhiteboxTool for SymmetricalDifference {
                             }
 
                             for record_num in 0..overlay.num_records {
+                                reporter.check_cancelled()?;
                                 let record = overlay.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i + num_points_input] {
@@ -526,6 +486,7 @@ impl WhiteboxTool for SymmetricalDifference {
                         }
                         ShapeTypeDimension::Measure => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i] {
@@ -538,6 +499,7 @@ impl WhiteboxTool for SymmetricalDifference {
                             }
 
                             for record_num in 0..overlay.num_records {
+                                reporter.check_cancelled()?;
                                 let record = overlay.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i + num_points_input] {
@@ -551,6 +513,7 @@ impl WhiteboxTool for SymmetricalDifference {
                         }
                         ShapeTypeDimension::Z => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i] {
@@ -564,6 +527,7 @@ impl WhiteboxTool for SymmetricalDifference {
                             }
 
                             for record_num in 0..overlay.num_records {
+                                reporter.check_cancelled()?;
                                 let record = overlay.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if output_point[i + num_points_input] {
@@ -592,6 +556,7 @@ impl WhiteboxTool for SymmetricalDifference {
                 let mut last_point_in_part: usize;
                 let mut polylines: Vec<Polyline> = vec![];
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -615,6 +580,7 @@ impl WhiteboxTool for SymmetricalDifference {
                 }
 
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -675,10 +641,11 @@ impl WhiteboxTool for SymmetricalDifference {
 
                     num_neighbours.push(line_num_neighbours);
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -708,10 +675,11 @@ impl WhiteboxTool for SymmetricalDifference {
                     }
                     features_polylines.push(pl.clone());
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -720,6 +688,7 @@ impl WhiteboxTool for SymmetricalDifference {
                 // Find duplicate polylines and remove them
                 let mut duplicate = vec![false; features_polylines.len()];
                 for i in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     if !duplicate[i] {
                         for j in (i + 1)..features_polylines.len() {
                             if features_polylines[i] == features_polylines[j] {
@@ -734,6 +703,7 @@ impl WhiteboxTool for SymmetricalDifference {
                     }
                 }
                 for i in (0..features_polylines.len()).rev() {
+                    reporter.check_cancelled()?;
                     if duplicate[i] {
                         features_polylines.remove(i);
                     }
@@ -741,6 +711,7 @@ impl WhiteboxTool for SymmetricalDifference {
 
                 let mut fid = 1i32;
                 for i in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
                     sfg.add_part(&features_polylines[i].vertices);
                     output.add_record(sfg);
@@ -778,6 +749,7 @@ impl WhiteboxTool for SymmetricalDifference {
 
                 // Read in the features
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     let mut mpl = MultiPolyline::new(record_num);
                     let mut holes = vec![false; record.num_parts as usize];
@@ -806,6 +778,7 @@ impl WhiteboxTool for SymmetricalDifference {
                 }
 
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     let mut mpl = MultiPolyline::new(record_num);
                     let mut holes = vec![false; record.num_parts as usize];
@@ -1902,12 +1875,13 @@ impl WhiteboxTool for SymmetricalDifference {
                         output.attributes.add_record(out_atts, false);
                     }
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (record_num + 1) as f64
                             / multipolylines.len() as f64)
                             as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
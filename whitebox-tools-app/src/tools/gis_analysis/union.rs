@@ -170,66 +170,22 @@ impl WhiteboxTool for Union {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut overlay_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut overlay_file = parsed.get_string("overlay").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut precision = std::f64::EPSILON;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val.contains("-input") {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-overlay" {
-                overlay_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-snap" {
-                precision = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if precision == 0f64 {
-                    precision = std::f64::EPSILON;
-                }
+        if let Some(value) = parsed.get_f64("snap") {
+            precision = value;
+            if precision == 0f64 {
+                precision = std::f64::EPSILON;
             }
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -246,15 +202,9 @@ impl WhiteboxTool for Union {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !overlay_file.contains(&sep) && !overlay_file.contains("/") {
-            overlay_file = format!("{}{}", working_directory, overlay_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        overlay_file = resolve_input_path(&overlay_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -319,11 +269,13 @@ impl WhiteboxTool for Union {
                 let mut tree = KdTree::with_capacity(dimensions, capacity_per_node);
                 let mut p: Point2D;
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     p = record.points[0];
                     tree.add([p.x, p.y], (1, record_num)).unwrap();
                 }
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     p = record.points[0];
                     tree.add([p.x, p.y], (2, record_num)).unwrap();
@@ -382,10 +334,11 @@ impl WhiteboxTool for Union {
                         }
                         output.attributes.add_record(out_atts, false);
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * record_num as f64 / num_total_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -419,11 +372,12 @@ impl WhiteboxTool for Union {
                         }
                         output.attributes.add_record(out_atts, false);
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (record_num + input.num_records) as f64
                             / num_total_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -437,6 +391,7 @@ impl WhiteboxTool for Union {
                 let mut p: Point2D;
                 let mut total_points = 0;
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for p in &record.points {
                         tree.add([p.x, p.y], 1).unwrap();
@@ -445,6 +400,7 @@ impl WhiteboxTool for Union {
                 }
                 let num_points_input = total_points;
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     for p in &record.points {
                         tree.add([p.x, p.y], 2).unwrap();
@@ -472,11 +428,12 @@ impl WhiteboxTool for Union {
                                 break;
                             }
                         }
+                        reporter.check_cancelled()?;
                         if verbose {
                             progress = (100.0_f64 * (i + num_points_input) as f64
                                 / num_total_points) as usize;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                                 old_progress = progress;
                             }
                         }
@@ -496,6 +453,7 @@ impl WhiteboxTool for Union {
                     match input.header.shape_type.dimension() {
                         ShapeTypeDimension::XY => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     sfg.add_point((record.points[i]).clone());
@@ -503,6 +461,7 @@ impl WhiteboxTool for Union {
                             }
 
                             for record_num in 0..overlay.num_records {
+                                reporter.check_cancelled()?;
                                 let record = overlay.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if !overlapped_point[i + num_points_input] {
@@ -513,6 +472,7 @@ impl WhiteboxTool for Union {
                         }
                         ShapeTypeDimension::Measure => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     sfg.add_pointm((record.points[i]).clone(), record.m_array[i]);
@@ -520,6 +480,7 @@ impl WhiteboxTool for Union {
                             }
 
                             for record_num in 0..overlay.num_records {
+                                reporter.check_cancelled()?;
                                 let record = overlay.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if !overlapped_point[i + num_points_input] {
@@ -533,6 +494,7 @@ impl WhiteboxTool for Union {
                         }
                         ShapeTypeDimension::Z => {
                             for record_num in 0..input.num_records {
+                                reporter.check_cancelled()?;
                                 let record = input.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     sfg.add_pointz(
@@ -544,6 +506,7 @@ impl WhiteboxTool for Union {
                             }
 
                             for record_num in 0..overlay.num_records {
+                                reporter.check_cancelled()?;
                                 let record = overlay.get_record(record_num);
                                 for i in 0..record.points.len() {
                                     if !overlapped_point[i + num_points_input] {
@@ -571,6 +534,7 @@ impl WhiteboxTool for Union {
                 let mut last_point_in_part: usize;
                 let mut polylines: Vec<Polyline> = vec![];
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -591,6 +555,7 @@ impl WhiteboxTool for Union {
                 }
 
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     for part in 0..record.num_parts as usize {
                         first_point_in_part = record.parts[part] as usize;
@@ -643,10 +608,11 @@ impl WhiteboxTool for Union {
 
                     num_neighbours.push(line_num_neighbours);
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -676,10 +642,11 @@ impl WhiteboxTool for Union {
                     }
                     features_polylines.push(pl.clone());
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (i + 1) as f64 / polylines.len() as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -687,6 +654,7 @@ impl WhiteboxTool for Union {
 
                 // Remove any zero-length line segments
                 for i in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     for j in (1..features_polylines[i].len()).rev() {
                         if features_polylines[i][j].nearly_equals(&features_polylines[i][j - 1]) {
                             features_polylines[i].remove(j);
@@ -697,6 +665,7 @@ impl WhiteboxTool for Union {
                 // Remove any single-point lines result from above.
                 let mut features_polylines2: Vec<Polyline> = vec![];
                 for i in (0..features_polylines.len()).rev() {
+                    reporter.check_cancelled()?;
                     if features_polylines[i].len() > 1 {
                         features_polylines2.push(features_polylines[i].clone());
                     }
@@ -749,6 +718,7 @@ impl WhiteboxTool for Union {
                 let mut p1: Point2D;
                 let mut p2: Point2D;
                 for i in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     p1 = features_polylines[i].first_vertex();
                     tree.add([p1.x, p1.y], first_node_id(i)).unwrap();
 
@@ -786,14 +756,12 @@ impl WhiteboxTool for Union {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress =
                             (100.0_f64 * (i + 1) as f64 / features_polylines.len() as f64) as usize;
                         if progress != old_progress {
-                            println!(
-                                "Finding duplicate polylines ({}): {}%",
-                                num_duplicates, progress
-                            );
+                            reporter.progress(&format!("Finding duplicate polylines ({})", num_duplicates), progress);
                             old_progress = progress;
                         }
                     }
@@ -801,6 +769,7 @@ impl WhiteboxTool for Union {
 
                 let mut fid = 1i32;
                 for i in 0..features_polylines.len() {
+                    reporter.check_cancelled()?;
                     if duplicate[i] && features_polylines[i].source_file == 1 {
                         let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
                         sfg.add_part(&features_polylines[i].vertices);
@@ -865,6 +834,7 @@ impl WhiteboxTool for Union {
 
                 // Read in the features
                 for record_num in 0..overlay.num_records {
+                    reporter.check_cancelled()?;
                     let record = overlay.get_record(record_num);
                     let mut mpl = MultiPolyline::new(record_num);
                     let mut holes = vec![false; record.num_parts as usize];
@@ -893,6 +863,7 @@ impl WhiteboxTool for Union {
                 }
 
                 for record_num in 0..input.num_records {
+                    reporter.check_cancelled()?;
                     let record = input.get_record(record_num);
                     let mut mpl = MultiPolyline::new(record_num);
                     let mut holes = vec![false; record.num_parts as usize];
@@ -1989,12 +1960,13 @@ impl WhiteboxTool for Union {
                         output.attributes.add_record(out_atts, false);
                     }
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (record_num + 1) as f64
                             / multipolylines.len() as f64)
                             as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -10,7 +10,7 @@ use whitebox_raster::*;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool will assign the *NoData* valued cells in an input raster (`--input1`) the
@@ -40,7 +40,7 @@ impl UpdateNodataCells {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input File 1".to_owned(),
-            flags: vec!["--input1".to_owned()],
+            flags: vec!["--i1".to_owned(), "--input1".to_owned()],
             description: "Input raster file 1.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -49,7 +49,7 @@ impl UpdateNodataCells {
 
         parameters.push(ToolParameter {
             name: "Input File 2 (Update Layer)".to_owned(),
-            flags: vec!["--input2".to_owned()],
+            flags: vec!["--i2".to_owned(), "--input2".to_owned()],
             description: "Input raster file 2; update layer.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -127,47 +127,12 @@ impl WhiteboxTool for UpdateNodataCells {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1 = String::new();
-        let mut input_file2 = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i1" || flag_val == "-input1" {
-                input_file1 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-i2" || flag_val == "-input2" {
-                input_file2 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -180,20 +145,12 @@ impl WhiteboxTool for UpdateNodataCells {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -207,10 +164,7 @@ impl WhiteboxTool for UpdateNodataCells {
         let rows = input1.configs.rows as isize;
         let columns = input1.configs.columns as isize;
         if input2.configs.rows != rows as usize || input2.configs.columns != columns as usize {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input rasters must have the same dimensions (i.e. number of rows and columns).",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input rasters must have the same dimensions (i.e. number of rows and columns)."));
         }
 
         let nodata1 = input1.configs.nodata;
@@ -225,17 +179,18 @@ impl WhiteboxTool for UpdateNodataCells {
         for row in 0..rows {
             for col in 0..columns {
                 z1 = input1.get_value(row, col);
-                if z1 == nodata1 {
+                if is_nodata(z1, nodata1) {
                     z2 = input2.get_value(row, col);
-                    if z2 != nodata2 {
+                    if !is_nodata(z2, nodata2) {
                         output.set_value(row, col, z2);
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
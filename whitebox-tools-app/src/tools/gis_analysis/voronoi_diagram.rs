@@ -13,7 +13,7 @@ use whitebox_vector::*;
 use std::collections::HashMap;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool creates a vector Voronoi diagram for a set of vector points. The
@@ -147,43 +147,14 @@ impl WhiteboxTool for VoronoiDiagram {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -200,13 +171,9 @@ impl WhiteboxTool for VoronoiDiagram {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Shapefile::read(&input_file)?;
 
@@ -244,11 +211,12 @@ impl WhiteboxTool for VoronoiDiagram {
                 record_numbers.push(record_num);
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * (record_num + 1) as f64 / input.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Reading points: {}%", progress);
+                    reporter.progress("Reading points", progress);
                     old_progress = progress;
                 }
             }
@@ -271,6 +239,7 @@ impl WhiteboxTool for VoronoiDiagram {
         let gap = expansion / 3f64; // One-third the average point spacing
         let mut num_edge_points = ((ghost_box.max_x - ghost_box.min_x) / gap) as usize;
         for x in 0..num_edge_points {
+            reporter.check_cancelled()?;
             points.push(Point2D::new(
                 ghost_box.min_x + x as f64 * gap,
                 ghost_box.min_y,
@@ -283,6 +252,7 @@ impl WhiteboxTool for VoronoiDiagram {
 
         num_edge_points = ((ghost_box.max_y - ghost_box.min_y) / gap) as usize;
         for y in 0..num_edge_points {
+            reporter.check_cancelled()?;
             points.push(Point2D::new(
                 ghost_box.min_x,
                 ghost_box.min_y + y as f64 * gap,
@@ -309,11 +279,12 @@ impl WhiteboxTool for VoronoiDiagram {
             if !point_edge_map.contains_key(&endpoint) || delaunay.halfedges[edge] == EMPTY {
                 point_edge_map.insert(endpoint, edge);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * edge as f64 / (delaunay.triangles.len() - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -359,10 +330,11 @@ impl WhiteboxTool for VoronoiDiagram {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * p as f64 / (input.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Creating Voronoi cells: {}%", progress);
+                    reporter.progress("Creating Voronoi cells", progress);
                     old_progress = progress;
                 }
             }
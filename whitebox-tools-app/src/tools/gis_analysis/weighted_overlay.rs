@@ -165,74 +165,15 @@ impl WhiteboxTool for WeightedOverlay {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files = String::new();
-        let mut weights_list = String::new();
-        let mut cost_list = String::new();
-        let mut constraint_files = String::new();
-        let mut output_file = String::new();
-        let mut scale_max = 1f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-factors" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-w" || flag_val == "-weights" {
-                weights_list = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-cost" {
-                cost_list = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-constraints" {
-                constraint_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-scale_max" {
-                scale_max = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_string("factors").unwrap_or_default();
+        let mut weights_list = parsed.get_string("weights").unwrap_or_default();
+        let cost_list = parsed.get_string("cost").unwrap_or_default();
+        let constraint_files = parsed.get_string("constraints").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let scale_max = parsed.get_f64("scale_max").unwrap_or(1f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -245,14 +186,10 @@ impl WhiteboxTool for WeightedOverlay {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut cmd = input_files.split(";");
         let mut vec = cmd.collect::<Vec<&str>>();
@@ -346,9 +283,7 @@ impl WhiteboxTool for WeightedOverlay {
                 };
 
                 let mut input_file = value.trim().to_string();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 let input = Raster::new(&input_file, "r")?;
                 in_nodata = input.configs.nodata;
                 if !read_first_file {
@@ -364,8 +299,7 @@ impl WhiteboxTool for WeightedOverlay {
                 // check to ensure that all inputs have the same rows and columns
                 if input.configs.rows as isize != rows || input.configs.columns as isize != columns
                 {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                "The input files must have the same number of rows and columns and spatial extent."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
                 }
 
                 min_val = input.configs.minimum;
@@ -373,9 +307,9 @@ impl WhiteboxTool for WeightedOverlay {
 
                 for row in 0..rows {
                     for col in 0..columns {
-                        if output.get_value(row, col) != out_nodata {
+                        if !is_nodata(output.get_value(row, col), out_nodata) {
                             in_val = input.get_value(row, col);
-                            if in_val != in_nodata {
+                            if !is_nodata(in_val, in_nodata) {
                                 in_val = (in_val - min_val) / range;
                                 if cost[j] {
                                     in_val = 1.0 - in_val;
@@ -387,13 +321,11 @@ impl WhiteboxTool for WeightedOverlay {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!(
-                                "Processing Factors (loop {} of {}): {}%",
-                                i, num_files, progress
-                            );
+                            reporter.progress(&format!("Processing Factors (loop {} of {})", i, num_files), progress);
                             old_progress = progress;
                         }
                     }
@@ -420,36 +352,31 @@ impl WhiteboxTool for WeightedOverlay {
                 };
 
                 let mut input_file = value.trim().to_string();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 let input = Raster::new(&input_file, "r")?;
                 in_nodata = input.configs.nodata;
                 // check to ensure that all inputs have the same rows and columns
                 if input.configs.rows as isize != rows || input.configs.columns as isize != columns
                 {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                "The input files must have the same number of rows and columns and spatial extent."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
                 }
 
                 for row in 0..rows {
                     for col in 0..columns {
                         in_val = input.get_value(row, col);
-                        if in_val != in_nodata && in_val <= 0f64 {
-                            if output.get_value(row, col) != out_nodata {
+                        if !is_nodata(in_val, in_nodata) && in_val <= 0f64 {
+                            if !is_nodata(output.get_value(row, col), out_nodata) {
                                 output.set_value(row, col, 0f64);
                             }
-                        } else if in_val == in_nodata {
+                        } else if is_nodata(in_val, in_nodata) {
                             output.set_value(row, col, out_nodata);
                         } // else it stays unaltered
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!(
-                                "Processing Constraints (loop {} of {}): {}%",
-                                i, num_constraints, progress
-                            );
+                            reporter.progress(&format!("Processing Constraints (loop {} of {})", i, num_constraints), progress);
                             old_progress = progress;
                         }
                     }
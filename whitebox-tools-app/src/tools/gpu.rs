@@ -0,0 +1,615 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: GPU evaluation of focal (moving-window) operations, used when a tool is run with the
+--compute=gpu flag. The GPU code is only compiled with the 'gpu' cargo feature. Every entry
+point returns None when the operation cannot be performed on a GPU, in which case the calling
+tool carries on with its CPU implementation. Calculations are performed in single precision;
+to limit the loss of precision for data with large offsets (e.g. elevations), values are
+shifted by a reference value before being transferred to the GPU.
+
+The convolution kernel serves all of the tools that use the shared convolution engine of the
+image_analysis module (e.g. MeanFilter and UserDefinedWeightsFilter), which tries the GPU first.
+*/
+
+use whitebox_raster::{is_nodata, Raster};
+
+/// A grid of values read by a focal operation.
+#[cfg_attr(not(feature = "gpu"), allow(dead_code))]
+pub(crate) trait FocalGrid {
+    fn rows(&self) -> usize;
+    fn columns(&self) -> usize;
+    /// The value of a cell, or `None` if it is NoData or beyond the grid.
+    fn value(&self, row: isize, column: isize) -> Option<f64>;
+}
+
+impl FocalGrid for Raster {
+    fn rows(&self) -> usize {
+        self.configs.rows
+    }
+
+    fn columns(&self) -> usize {
+        self.configs.columns
+    }
+
+    fn value(&self, row: isize, column: isize) -> Option<f64> {
+        let z = self.get_value(row, column);
+        if !is_nodata(z, self.configs.nodata) {
+            Some(z)
+        } else {
+            None
+        }
+    }
+}
+
+/// A focal operation that can be evaluated on the GPU.
+#[cfg_attr(not(feature = "gpu"), allow(dead_code))]
+pub(crate) enum FocalKernel<'a> {
+    /// NoData-aware convolution with a kernel of `weights`, `rows` by `columns` in size, stored in
+    /// row-major order; the kernel cell at `center_row` and `center_col` is aligned with the
+    /// filtered cell. `mode` is 0 to normalize each output value by the sum of the weights of the
+    /// valid neighbours, 1 to skip NoData neighbours, and 2 to replace NoData neighbours with the
+    /// value of the filtered cell.
+    Convolution {
+        weights: &'a [f64],
+        rows: usize,
+        columns: usize,
+        center_row: usize,
+        center_col: usize,
+        mode: u32,
+    },
+    /// Slope gradient using the 5 x 5 polynomial of Florinsky (2016); `units` is 1 for degrees,
+    /// 2 for radians, and 3 for percent.
+    Slope { z_factor: f64, res: f64, units: u32 },
+    /// Hillshade illumination (Horn's 3 x 3 method) before scaling, with one z-factor per row.
+    /// `azimuth` and `altitude` are in radians, `azimuth` already rotated as in `Hillshade`.
+    Hillshade { z_factors: &'a [f64], res: f64, azimuth: f64, altitude: f64 },
+}
+
+impl<'a> FocalKernel<'a> {
+    /// The number of rows and columns of neighbours that the kernel reads on each side of a cell.
+    #[cfg_attr(not(feature = "gpu"), allow(dead_code))]
+    fn halo(&self) -> usize {
+        match self {
+            FocalKernel::Convolution { rows, center_row, .. } => {
+                (*center_row).max(rows - 1 - center_row)
+            }
+            FocalKernel::Slope { .. } => 2,
+            FocalKernel::Hillshade { .. } => 1,
+        }
+    }
+}
+
+/// Returns `true` if the settings request GPU computation, i.e. the `--compute=gpu` flag.
+pub(crate) fn gpu_requested() -> bool {
+    match whitebox_common::configs::get_configs() {
+        Ok(configs) => configs.compute.trim().to_lowercase() == "gpu",
+        Err(_) => false,
+    }
+}
+
+/// Evaluates `kernel` for every cell of `input` on the GPU, returning the output rows, in which
+/// NoData cells hold `out_nodata`. Returns `None`, after printing the reason when `verbose`, if
+/// the operation could not be performed on a GPU.
+pub(crate) fn run_focal(
+    input: &Raster,
+    kernel: &FocalKernel,
+    out_nodata: f64,
+    verbose: bool,
+) -> Option<Vec<Vec<f64>>> {
+    run_focal_grid(input, kernel, out_nodata, verbose)
+}
+
+/// Evaluates `kernel` for every cell of any `FocalGrid`, in the same way as `run_focal`.
+#[cfg(not(feature = "gpu"))]
+pub(crate) fn run_focal_grid(
+    _input: &dyn FocalGrid,
+    _kernel: &FocalKernel,
+    _out_nodata: f64,
+    verbose: bool,
+) -> Option<Vec<Vec<f64>>> {
+    if verbose {
+        println!("Warning: This build does not include GPU support; falling back to the CPU.");
+    }
+    None
+}
+
+#[cfg(feature = "gpu")]
+pub(crate) fn run_focal_grid(
+    input: &dyn FocalGrid,
+    kernel: &FocalKernel,
+    out_nodata: f64,
+    verbose: bool,
+) -> Option<Vec<Vec<f64>>> {
+    match device::run_focal(input, kernel, out_nodata, verbose) {
+        Ok(rows) => Some(rows),
+        Err(msg) => {
+            if verbose {
+                println!("Warning: {}; falling back to the CPU.", msg);
+            }
+            None
+        }
+    }
+}
+
+#[cfg(feature = "gpu")]
+mod device {
+    use super::{FocalGrid, FocalKernel};
+    use std::borrow::Cow;
+    use std::sync::mpsc;
+    use wgpu::util::DeviceExt;
+
+    /// NoData marker used within GPU buffers.
+    const GPU_NODATA: f32 = -3.0e38;
+
+    /// The largest input strip transferred to the GPU at once, in bytes.
+    const MAX_STRIP_BYTES: u64 = 64 * 1024 * 1024;
+
+    const SHADER: &str = r#"
+struct Params {
+    rows: u32,
+    columns: u32,
+    halo: u32,
+    kernel_columns: u32,
+    res: f32,
+    z_factor: f32,
+    units: u32,
+    azimuth: f32,
+    sin_theta: f32,
+    cos_theta: f32,
+    kernel_rows: u32,
+    center_row: u32,
+    center_col: u32,
+    mode: u32,
+    reference: f32,
+    pad0: u32,
+};
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> input: array<f32>;
+@group(0) @binding(2) var<storage, read_write> output: array<f32>;
+@group(0) @binding(3) var<storage, read> aux: array<f32>;
+
+const NODATA: f32 = -3.0e38;
+const PI: f32 = 3.141592653589793;
+
+fn is_nodata(z: f32) -> bool {
+    return z < -1.0e38;
+}
+
+// The value at row r and column c of the strip's output rows, or NODATA beyond the strip.
+fn value_at(r: i32, c: i32) -> f32 {
+    let rr = r + i32(params.halo);
+    if (c < 0 || c >= i32(params.columns) || rr < 0 || rr >= i32(params.rows + 2u * params.halo)) {
+        return NODATA;
+    }
+    return input[u32(rr) * params.columns + u32(c)];
+}
+
+@compute @workgroup_size(16, 16)
+fn convolve(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.columns || id.y >= params.rows) {
+        return;
+    }
+    let r = i32(id.y);
+    let c = i32(id.x);
+    let idx = id.y * params.columns + id.x;
+    if (is_nodata(value_at(r, c))) {
+        output[idx] = NODATA;
+        return;
+    }
+    let z = value_at(r, c);
+    var sum_w = 0.0;
+    var all_w = 0.0;
+    var total = 0.0;
+    for (var a = 0u; a < params.kernel_rows * params.kernel_columns; a = a + 1u) {
+        let kr = i32(a / params.kernel_columns) - i32(params.center_row);
+        let kc = i32(a % params.kernel_columns) - i32(params.center_col);
+        let zn = value_at(r + kr, c + kc);
+        all_w = all_w + aux[a];
+        if (!is_nodata(zn)) {
+            sum_w = sum_w + aux[a];
+            total = total + aux[a] * zn;
+        }
+    }
+    if (params.mode == 0u) {
+        if (sum_w > 0.0) {
+            output[idx] = total / sum_w;
+        } else {
+            output[idx] = NODATA;
+        }
+    } else if (params.mode == 1u) {
+        // the shift of the valid values can only be undone here, since it depends on sum_w
+        output[idx] = total + params.reference * sum_w;
+    } else {
+        output[idx] = total + z * (all_w - sum_w);
+    }
+}
+
+@compute @workgroup_size(16, 16)
+fn slope(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.columns || id.y >= params.rows) {
+        return;
+    }
+    let r = i32(id.y);
+    let c = i32(id.x);
+    let idx = id.y * params.columns + id.x;
+    let z12 = value_at(r, c);
+    if (is_nodata(z12)) {
+        output[idx] = NODATA;
+        return;
+    }
+    var z: array<f32, 25>;
+    for (var n = 0; n < 25; n = n + 1) {
+        var zn = value_at(r + (n % 5) - 2, c + (n / 5) - 2);
+        if (is_nodata(zn)) {
+            zn = z12;
+        }
+        z[n] = zn * params.z_factor;
+    }
+    let res = params.res;
+    let q = 1.0 / (420.0 * res) * (44.0 * (z[3] + z[23] - z[1] - z[21]) + 31.0 * (z[0] + z[20] - z[4] - z[24]
+        + 2.0 * (z[8] + z[18] - z[6] - z[16])) + 17.0 * (z[14] - z[10] + 4.0 * (z[13] - z[11]))
+        + 5.0 * (z[9] + z[19] - z[5] - z[15]));
+    let p = 1.0 / (420.0 * res) * (44.0 * (z[5] + z[9] - z[15] - z[19]) + 31.0 * (z[20] + z[24] - z[0] - z[4]
+        + 2.0 * (z[6] + z[8] - z[16] - z[18])) + 17.0 * (z[2] - z[22] + 4.0 * (z[7] - z[17]))
+        + 5.0 * (z[1] + z[3] - z[21] - z[23]));
+    let g = sqrt(p * p + q * q);
+    if (params.units == 1u) {
+        output[idx] = degrees(atan(g));
+    } else if (params.units == 2u) {
+        output[idx] = atan(g);
+    } else {
+        output[idx] = g * 100.0;
+    }
+}
+
+@compute @workgroup_size(16, 16)
+fn hillshade(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= params.columns || id.y >= params.rows) {
+        return;
+    }
+    let r = i32(id.y);
+    let c = i32(id.x);
+    let idx = id.y * params.columns + id.x;
+    let z0 = value_at(r, c);
+    if (is_nodata(z0)) {
+        output[idx] = NODATA;
+        return;
+    }
+    var d_x = array<i32, 8>(1, 1, 1, 0, -1, -1, -1, 0);
+    var d_y = array<i32, 8>(-1, 0, 1, 1, 1, 0, -1, -1);
+    let zf = aux[id.y];
+    let z = z0 * zf;
+    var n: array<f32, 8>;
+    for (var k = 0; k < 8; k = k + 1) {
+        let zn = value_at(r + d_y[k], c + d_x[k]);
+        if (is_nodata(zn)) {
+            n[k] = z;
+        } else {
+            n[k] = zn * zf;
+        }
+    }
+    let eight_grid_res = params.res * 8.0;
+    let fy = (n[6] - n[4] + 2.0 * (n[7] - n[3]) + n[0] - n[2]) / eight_grid_res;
+    let fx = (n[2] - n[4] + 2.0 * (n[1] - n[5]) + n[0] - n[6]) / eight_grid_res;
+    let tan_slope = max(sqrt(fx * fx + fy * fy), 0.00017);
+    var aspect = PI;
+    if (fx != 0.0) {
+        aspect = PI - atan(fy / fx) + PI / 2.0 * sign(fx);
+    }
+    let term1 = tan_slope / sqrt(1.0 + tan_slope * tan_slope);
+    let term2 = params.sin_theta / tan_slope;
+    let term3 = params.cos_theta * sin(params.azimuth - aspect);
+    output[idx] = term1 * (term2 - term3);
+}
+"#;
+
+    pub(super) fn run_focal(
+        input: &dyn FocalGrid,
+        kernel: &FocalKernel,
+        out_nodata: f64,
+        verbose: bool,
+    ) -> Result<Vec<Vec<f64>>, String> {
+        let rows = input.rows();
+        let columns = input.columns();
+        let halo = kernel.halo();
+        if rows == 0 || columns == 0 {
+            return Err("The input raster is empty".to_string());
+        }
+
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .ok_or("No GPU adapter is available")?;
+        if verbose {
+            println!("Using GPU: {}", adapter.get_info().name);
+        }
+        let limits = adapter.limits();
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                required_features: wgpu::Features::empty(),
+                required_limits: limits.clone(),
+                memory_hints: wgpu::MemoryHints::Performance,
+            },
+            None,
+        ))
+        .map_err(|e| format!("The GPU device could not be initialized ({})", e))?;
+
+        // Size the strips of rows processed by each dispatch to respect the device limits.
+        let max_bytes = MAX_STRIP_BYTES
+            .min(limits.max_storage_buffer_binding_size as u64)
+            .min(limits.max_buffer_size);
+        let row_bytes = (columns * 4) as u64;
+        let max_strip_rows = (max_bytes / row_bytes) as usize;
+        if max_strip_rows <= 2 * halo {
+            return Err("The input raster has too many columns for the GPU".to_string());
+        }
+        let strip_rows = (max_strip_rows - 2 * halo).min(rows);
+        if (columns + 15) / 16 > limits.max_compute_workgroups_per_dimension as usize {
+            return Err("The input raster has too many columns for the GPU".to_string());
+        }
+
+        // Shift values by a reference value to preserve precision in single-precision arithmetic.
+        // All of the supported kernels are invariant to such a shift, except the convolution,
+        // whose output is shifted back. The reference is representable in single precision, so
+        // that the shader can undo the shift too.
+        let mut reference = 0f64;
+        'find_ref: for row in 0..rows as isize {
+            for col in 0..columns as isize {
+                if let Some(z) = input.value(row, col) {
+                    reference = z as f32 as f64;
+                    break 'find_ref;
+                }
+            }
+        }
+
+        let (entry_point, aux, params) = match kernel {
+            FocalKernel::Convolution { weights, rows, columns, center_row, center_col, mode } => {
+                let aux: Vec<f32> = weights.iter().map(|w| *w as f32).collect();
+                let params = [
+                    0u32,
+                    0,
+                    0,
+                    *columns as u32,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    *rows as u32,
+                    *center_row as u32,
+                    *center_col as u32,
+                    *mode,
+                    (reference as f32).to_bits(),
+                    0,
+                ];
+                ("convolve", aux, params)
+            }
+            FocalKernel::Slope { z_factor, res, units } => {
+                let params = [
+                    0u32,
+                    0,
+                    0,
+                    0,
+                    (*res as f32).to_bits(),
+                    (*z_factor as f32).to_bits(),
+                    *units,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                ];
+                ("slope", vec![0f32], params)
+            }
+            FocalKernel::Hillshade { z_factors, res, azimuth, altitude } => {
+                let aux: Vec<f32> = z_factors.iter().map(|z| *z as f32).collect();
+                let params = [
+                    0u32,
+                    0,
+                    0,
+                    0,
+                    (*res as f32).to_bits(),
+                    0,
+                    0,
+                    (*azimuth as f32).to_bits(),
+                    (altitude.sin() as f32).to_bits(),
+                    (altitude.cos() as f32).to_bits(),
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                ];
+                ("hillshade", aux, params)
+            }
+        };
+        let per_row_aux = matches!(kernel, FocalKernel::Hillshade { .. });
+        let uses_aux = !matches!(kernel, FocalKernel::Slope { .. });
+
+        // Capture validation and allocation errors, which wgpu would otherwise treat as fatal, so
+        // that the tool can fall back to the CPU instead.
+        device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(SHADER)),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: entry_point,
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        let layout = pipeline.get_bind_group_layout(0);
+        check_errors(&device, "The GPU shader could not be compiled")?;
+
+        let mut output_rows: Vec<Vec<f64>> = Vec::with_capacity(rows);
+        let mut strip_start = 0usize;
+        while strip_start < rows {
+            let n = strip_rows.min(rows - strip_start);
+
+            let mut data = Vec::with_capacity((n + 2 * halo) * columns);
+            for r in strip_start as isize - halo as isize..(strip_start + n + halo) as isize {
+                for c in 0..columns as isize {
+                    data.push(match input.value(r, c) {
+                        Some(z) => (z - reference) as f32,
+                        None => GPU_NODATA,
+                    });
+                }
+            }
+            let mut strip_params = params;
+            strip_params[0] = n as u32;
+            strip_params[1] = columns as u32;
+            strip_params[2] = halo as u32;
+            let strip_aux: Vec<f32> = if per_row_aux {
+                aux[strip_start..strip_start + n].to_vec()
+            } else {
+                aux.clone()
+            };
+
+            device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+            device.push_error_scope(wgpu::ErrorFilter::Validation);
+            let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&strip_params),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+            let input_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&data),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+            let aux_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&strip_aux),
+                usage: wgpu::BufferUsages::STORAGE,
+            });
+            let out_size = (n * columns * 4) as u64;
+            let output_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: out_size,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+            });
+            let staging_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: out_size,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            let mut entries = vec![
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: params_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: input_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: output_buffer.as_entire_binding(),
+                },
+            ];
+            // the layout derived from the shader omits the auxiliary buffer if it is unused
+            if uses_aux {
+                entries.push(wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: aux_buffer.as_entire_binding(),
+                });
+            }
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &entries,
+            });
+
+            let mut encoder =
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: None,
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(&pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups(
+                    ((columns + 15) / 16) as u32,
+                    ((n + 15) / 16) as u32,
+                    1,
+                );
+            }
+            encoder.copy_buffer_to_buffer(&output_buffer, 0, &staging_buffer, 0, out_size);
+            queue.submit(Some(encoder.finish()));
+            check_errors(&device, "The GPU computation failed")?;
+
+            let slice = staging_buffer.slice(..);
+            let (tx, rx) = mpsc::channel();
+            slice.map_async(wgpu::MapMode::Read, move |result| {
+                let _ = tx.send(result);
+            });
+            device.poll(wgpu::Maintain::Wait);
+            match rx.recv() {
+                Ok(Ok(())) => {}
+                _ => return Err("The GPU output could not be read".to_string()),
+            }
+            {
+                let mapped = slice.get_mapped_range();
+                let values: &[f32] = bytemuck::cast_slice(&mapped);
+                let shift = match kernel {
+                    FocalKernel::Convolution { mode: 0, .. } => reference,
+                    FocalKernel::Convolution { mode: 2, weights, .. } => {
+                        reference * weights.iter().sum::<f64>()
+                    }
+                    _ => 0f64,
+                };
+                for r in 0..n {
+                    let row_data = values[r * columns..(r + 1) * columns]
+                        .iter()
+                        .map(|v| {
+                            if *v < -1.0e38 {
+                                out_nodata
+                            } else {
+                                *v as f64 + shift
+                            }
+                        })
+                        .collect();
+                    output_rows.push(row_data);
+                }
+            }
+            staging_buffer.unmap();
+
+            strip_start += n;
+        }
+
+        Ok(output_rows)
+    }
+
+    /// Pops the validation and out-of-memory error scopes pushed before a block of GPU work.
+    fn check_errors(device: &wgpu::Device, msg: &str) -> Result<(), String> {
+        let validation = pollster::block_on(device.pop_error_scope());
+        let out_of_memory = pollster::block_on(device.pop_error_scope());
+        match validation.or(out_of_memory) {
+            Some(e) => Err(format!("{} ({})", msg, e)),
+            None => Ok(()),
+        }
+    }
+}
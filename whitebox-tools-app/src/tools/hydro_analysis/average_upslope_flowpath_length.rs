@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool calculates the average length of the flowpaths that run through each grid cell (in map horizontal units)
 /// in in an input digital elevation model (DEM). The user must specify the name of
@@ -47,7 +46,7 @@ impl AverageUpslopeFlowpathLength {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -125,40 +124,11 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -171,17 +141,11 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -202,17 +166,13 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
         let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
         let mut num_flowpaths: Array2D<i64> = Array2D::new(rows, columns, 0, 0)?;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let nodata = input.configs.nodata;
                 let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
                 let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
@@ -235,13 +195,13 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
                                 z_n = input[(row + d_y[i], col + d_x[i])];
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -263,7 +223,9 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
                             data[col as usize] = -1i8;
                         }
                     }
-                    tx.send((row, data, interior_pit_found)).unwrap();
+                    if tx.send((row, data, interior_pit_found)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -275,10 +237,11 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
             if pit {
                 interior_pit_found = true;
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow directions: {}%", progress);
+                    reporter.progress("Flow directions", progress);
                     old_progress = progress;
                 }
             }
@@ -293,7 +256,7 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
             let input = input.clone();
             let flow_dir = flow_dir.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
                 let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let inflowing_vals: [i8; 8] = [4, 5, 6, 7, 0, 1, 2, 3];
@@ -303,7 +266,7 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if flow_dir[(row + d_y[i], col + d_x[i])] == inflowing_vals[i] {
@@ -315,7 +278,9 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
                             data[col as usize] = -1i8;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -337,10 +302,11 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -373,7 +339,7 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
                 row_n = row + d_y[dir as usize];
                 col_n = col + d_x[dir as usize];
                 length = output[(row, col)] + grid_lengths[dir as usize];
-                if output[(row_n, col_n)] == nodata {
+                if is_nodata(output[(row_n, col_n)], nodata) {
                     output[(row_n, col_n)] = length;
                 } else {
                     output.increment(row_n, col_n, length);
@@ -389,11 +355,12 @@ impl WhiteboxTool for AverageUpslopeFlowpathLength {
 
             output[(row, col)] = output[(row, col)] / num_flowpaths[(row, col)] as f64;
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flowpath tracing: {}%", progress);
+                    reporter.progress("Flowpath tracing", progress);
                     old_progress = progress;
                 }
             }
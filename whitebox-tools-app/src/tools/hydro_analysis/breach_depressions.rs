@@ -15,7 +15,7 @@ use std::collections::VecDeque;
 use std::env;
 use std::f64;
 use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to remove the depressions in a digital elevation model (DEM), a
@@ -74,7 +74,7 @@ impl BreachDepressions {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -189,86 +189,24 @@ impl WhiteboxTool for BreachDepressions {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut max_depth = f64::INFINITY;
         let mut max_length = f64::INFINITY;
         let mut constrained_mode = false;
-        let mut flat_increment = f64::NAN;
-        let mut fill_pits = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        let flat_increment = parsed.get_f64("flat_increment").unwrap_or(f64::NAN);
+        let fill_pits = parsed.get_bool("fill_pits");
+
+        if let Some(value) = parsed.get_f64("max_depth") {
+            max_depth = value;
+            constrained_mode = true;
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-max_depth" {
-                max_depth = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                constrained_mode = true;
-            } else if flag_val == "-max_length" {
-                max_length = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                constrained_mode = true;
-            } else if flag_val == "-flat_increment" {
-                flat_increment = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-fill_pits" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    fill_pits = true;
-                }
-            }
+        if let Some(value) = parsed.get_f64("max_length") {
+            max_length = value;
+            constrained_mode = true;
         }
 
         if verbose {
@@ -282,17 +220,11 @@ impl WhiteboxTool for BreachDepressions {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -333,15 +265,15 @@ impl WhiteboxTool for BreachDepressions {
             for row in 1..rows - 1 {
                 for col in 1..columns - 1 {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         flag = true;
                         min_zn = f64::INFINITY;
                         for n in 0..8 {
                             z_n = input.get_value(row + dy[n], col + dx[n]);
-                            if z_n < min_zn && z_n != nodata {
+                            if z_n < min_zn && !is_nodata(z_n, nodata) {
                                 min_zn = z_n;
                             }
-                            if z_n < z && z_n != nodata {
+                            if z_n < z && !is_nodata(z_n, nodata) {
                                 flag = false;
                                 break;
                             }
@@ -351,10 +283,11 @@ impl WhiteboxTool for BreachDepressions {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Filling pits: {}%", progress);
+                        reporter.progress("Filling pits", progress);
                         old_progress = progress;
                     }
                 }
@@ -381,6 +314,7 @@ impl WhiteboxTool for BreachDepressions {
         let mut queue: VecDeque<(isize, isize)> =
             VecDeque::with_capacity((rows * columns) as usize);
         for row in 0..rows {
+            reporter.check_cancelled()?;
             /*
             Note that this is only possible because Whitebox rasters
             allow you to address cells beyond the raster extent but
@@ -417,7 +351,7 @@ impl WhiteboxTool for BreachDepressions {
                 zin_n = input.get_value(row_n, col_n);
                 zout_n = output.get_value(row_n, col_n);
                 if zout_n == background_val {
-                    if zin_n == nodata {
+                    if is_nodata(zin_n, nodata) {
                         output.set_value(row_n, col_n, nodata);
                         queue.push_back((row_n, col_n));
                     } else {
@@ -433,10 +367,11 @@ impl WhiteboxTool for BreachDepressions {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -461,7 +396,7 @@ impl WhiteboxTool for BreachDepressions {
                     zout_n = output.get_value(row_n, col_n);
                     if zout_n == background_val {
                         zin_n = input.get_value(row_n, col_n);
-                        if zin_n != nodata {
+                        if !is_nodata(zin_n, nodata) {
                             flow_dir.set_value(row_n, col_n, back_link[n]);
                             output.set_value(row_n, col_n, zin_n);
                             minheap.push(GridCell {
@@ -502,7 +437,7 @@ impl WhiteboxTool for BreachDepressions {
                                 for n2 in 0..8 {
                                     let row2 = cell.0 + dy[n2];
                                     let col2 = cell.1 + dx[n2];
-                                    if input.get_value(row2, col2) == nodata
+                                    if is_nodata(input.get_value(row2, col2), nodata)
                                         && output.get_value(row2, col2) == background_val
                                     {
                                         if row2 >= 0 && row2 < rows && col2 >= 0 && col2 < columns {
@@ -517,12 +452,13 @@ impl WhiteboxTool for BreachDepressions {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     num_solved_cells += 1;
                     progress =
                         (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -548,7 +484,7 @@ impl WhiteboxTool for BreachDepressions {
                     zout_n = output.get_value(row_n, col_n);
                     if zout_n == background_val {
                         zin_n = input.get_value(row_n, col_n);
-                        if zin_n != nodata {
+                        if !is_nodata(zin_n, nodata) {
                             flow_dir.set_value(row_n, col_n, back_link[n]);
                             output.set_value(row_n, col_n, zin_n);
                             minheap.push(GridCell {
@@ -711,7 +647,7 @@ impl WhiteboxTool for BreachDepressions {
                                 for n2 in 0..8 {
                                     let row2 = cell.0 + dy[n2];
                                     let col2 = cell.1 + dx[n2];
-                                    if input.get_value(row2, col2) == nodata
+                                    if is_nodata(input.get_value(row2, col2), nodata)
                                         && output.get_value(row2, col2) == background_val
                                     {
                                         if row2 >= 0 && row2 < rows && col2 >= 0 && col2 < columns {
@@ -726,12 +662,13 @@ impl WhiteboxTool for BreachDepressions {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     num_solved_cells += 1;
                     progress =
                         (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -754,19 +691,20 @@ impl WhiteboxTool for BreachDepressions {
                             row_n = row + dy[dir as usize];
                             col_n = col + dx[dir as usize];
                             z_n = output.get_value(row_n, col_n);
-                            if z_n != nodata {
+                            if !is_nodata(z_n, nodata) {
                                 if z <= z_n + small_num {
                                     output.set_value(row, col, z_n + small_num);
                                 }
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         num_solved_cells += 1;
                         progress =
                             (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Filling DEM: {}%", progress);
+                            reporter.progress("Filling DEM", progress);
                             old_progress = progress;
                         }
                     }
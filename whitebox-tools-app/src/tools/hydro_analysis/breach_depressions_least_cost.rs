@@ -15,11 +15,10 @@ use std::collections::{BinaryHeap, VecDeque};
 use std::env;
 use std::f64;
 use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool can be used to perform a type of optimal depression breaching to prepare a
 /// digital elevation model (DEM) for hydrological analysis. Depression breaching is a common
@@ -111,7 +110,7 @@ impl BreachDepressionsLeastCost {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -151,7 +150,7 @@ impl BreachDepressionsLeastCost {
             description: "Optional flag indicating whether to minimize breach distances."
                 .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -171,7 +170,7 @@ impl BreachDepressionsLeastCost {
                 "Optional flag indicating whether to fill any remaining unbreached depressions."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -237,83 +236,16 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut max_cost = f64::INFINITY;
-        let mut max_dist = 20isize;
-        let mut flat_increment = f64::NAN;
-        let mut fill_deps = false;
-        let mut minimize_dist = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-dist" {
-                max_dist = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-max_cost" {
-                max_cost = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-flat_increment" {
-                flat_increment = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-min_dist" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    minimize_dist = true;
-                }
-            } else if flag_val == "-fill" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    fill_deps = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let max_cost = parsed.get_f64("max_cost").unwrap_or(f64::INFINITY);
+        let max_dist = parsed.get_i64("dist").map(|v| v as isize).unwrap_or(20isize);
+        let flat_increment = parsed.get_f64("flat_increment").unwrap_or(f64::NAN);
+        let fill_deps = parsed.get_bool("fill");
+        let minimize_dist = parsed.get_bool("min_dist");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -326,17 +258,11 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -369,12 +295,9 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
         let mut length: i16;
         let mut length_n: i16;
         let mut b: usize;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let pool = whitebox_common::utils::ThreadPool::new(&configs);
+        let num_procs = pool.num_threads() as isize;
 
         let small_num = if !flat_increment.is_nan() || flat_increment == 0f64 {
             flat_increment
@@ -395,7 +318,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            pool.spawn(tid as usize, move || {
                 let (mut z, mut zn, mut min_zn): (f64, f64, f64);
                 let mut flag: bool;
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
@@ -405,7 +328,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                     let mut pits = vec![];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             flag = true;
                             min_zn = f64::INFINITY;
                             for n in 0..8 {
@@ -413,7 +336,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                 if zn < min_zn {
                                     min_zn = zn;
                                 }
-                                if zn == nodata {
+                                if is_nodata(zn, nodata) {
                                     // It's an edge cell.
                                     flag = false;
                                     break;
@@ -430,7 +353,9 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                             }
                         }
                     }
-                    tx.send((row, data, pits)).unwrap();
+                    if tx.send((row, data, pits)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -442,10 +367,11 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
             output.set_row_data(row, data);
             undefined_flow_cells.append(&mut pits);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding pits: {}%", progress);
+                    reporter.progress("Finding pits", progress);
                     old_progress = progress;
                 }
             }
@@ -483,7 +409,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
             flag = true;
             for n in 0..8 {
                 zn = output.get_value(row + dy[n], col + dx[n]);
-                if zn < z && zn != nodata {
+                if zn < z && !is_nodata(zn, nodata) {
                     // It has a lower non-nodata cell
                     // Resolving some other pit cell resulted in a solution for this one.
                     num_solved += 1;
@@ -528,7 +454,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                             backlink.set_value(rn, cn, backlink_dir[n]);
                             zn = output.get_value(rn, cn);
                             zout = z - (length_n as f64 * small_num);
-                            if zn > zout && zn != nodata {
+                            if zn > zout && !is_nodata(zn, nodata) {
                                 cost2 = zn - zout;
                                 new_cost = if minimize_dist {
                                     accum + (cost1 + cost2) / 2f64 * cost_dist[n]
@@ -543,7 +469,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                         priority: new_cost,
                                     });
                                 }
-                            } else if zn <= zout || zn == nodata {
+                            } else if zn <= zout || is_nodata(zn, nodata) {
                                 // We're at a cell that we can breach to
                                 while flag {
                                     // Find which cell to go to from here
@@ -583,12 +509,13 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64
                     * (1f64 - (undefined_flow_cells.len()) as f64 / (num_deps - 1) as f64))
                     as usize;
                 if progress != old_progress {
-                    println!("Breaching: {}%", progress);
+                    reporter.progress("Breaching", progress);
                     old_progress = progress;
                 }
             }
@@ -609,7 +536,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
             for tid in 0..num_procs {
                 let output2 = output2.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                pool.spawn(tid as usize, move || {
                     let mut z: f64;
                     let mut zn: f64;
                     let mut flag: bool;
@@ -617,11 +544,11 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                     for row in (1..rows - 1).filter(|r| r % num_procs == tid) {
                         for col in 1..columns - 1 {
                             z = output2.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 flag = true;
                                 for n in 0..8 {
                                     zn = output2.get_value(row + dy[n], col + dx[n]);
-                                    if zn < z || zn == nodata {
+                                    if zn < z || is_nodata(zn, nodata) {
                                         // It either has a lower neighbour or is an edge cell.
                                         flag = false;
                                         break;
@@ -634,7 +561,9 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                             }
                         }
                     }
-                    tx.send(pits).unwrap();
+                    if tx.send(pits).is_err() {
+                        return;
+                    }
                 });
             }
 
@@ -643,10 +572,11 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                 let mut pits = rx.recv().expect("Error receiving data from thread.");
                 undefined_flow_cells.append(&mut pits);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (p + 1) as f64 / num_procs as f64) as usize;
                     if progress != old_progress {
-                        println!("Finding pit cells: {}%", progress);
+                        reporter.progress("Finding pit cells", progress);
                         old_progress = progress;
                     }
                 }
@@ -698,14 +628,14 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                 if visited.get_value(rn, cn) == 0 {
                                     zn = output.get_value(rn, cn);
                                     if !outlet_found {
-                                        if zn >= z && zn != nodata {
+                                        if zn >= z && !is_nodata(zn, nodata) {
                                             minheap.push(GridCell {
                                                 row: rn,
                                                 column: cn,
                                                 priority: zn,
                                             });
                                             visited.set_value(rn, cn, 1);
-                                        } else if zn != nodata {
+                                        } else if !is_nodata(zn, nodata) {
                                             // zn < z
                                             // 'cell' has a lower neighbour that hasn't already passed through minheap.
                                             // Therefore, 'cell' is a pour point cell.
@@ -778,10 +708,11 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * pit_id as f64 / num_deps as f64) as usize;
                     if progress != old_progress {
-                        println!("Filling depressions: {}%", progress);
+                        reporter.progress("Filling depressions", progress);
                         old_progress = progress;
                     }
                 }
@@ -800,13 +731,14 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                 // let mut queue = VecDeque::new();
                 minheap.clear();
                 while let Some(cell) = possible_outlets.pop() {
+                    reporter.check_cancelled()?;
                     z = output.get_value(cell.0, cell.1);
                     flag = false;
                     for n in 0..8 {
                         rn = cell.0 + dy[n];
                         cn = cell.1 + dx[n];
                         zn = output.get_value(rn, cn);
-                        if zn < z && zn != nodata {
+                        if zn < z && !is_nodata(zn, nodata) {
                             flag = true;
                             break;
                         }
@@ -860,7 +792,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                 cn = cell2.column + dx[n];
                                 if flats.get_value(rn, cn) != 3 {
                                     zn = output.get_value(rn, cn);
-                                    if zn == z && zn != nodata {
+                                    if zn == z && !is_nodata(zn, nodata) {
                                         // queue.push_back((rn, cn, z));
                                         minheap2.push(GridCell2 {
                                             row: rn,
@@ -882,7 +814,7 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                                 cn = cell2.column + dx[n];
                                 if flats.get_value(rn, cn) != 3 {
                                     zn = output.get_value(rn, cn);
-                                    if zn < z + small_num && zn >= cell2.z && zn != nodata {
+                                    if zn < z + small_num && zn >= cell2.z && !is_nodata(zn, nodata) {
                                         // queue.push_back((rn, cn, cell2.2));
                                         minheap2.push(GridCell2 {
                                             row: rn,
@@ -898,11 +830,12 @@ impl WhiteboxTool for BreachDepressionsLeastCost {
                         }
                     }
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (1f64 - minheap.len() as f64 / num_outlets as f64))
                             as usize;
                         if progress != old_progress {
-                            println!("Fixing flats: {}%", progress);
+                            reporter.progress("Fixing flats", progress);
                             old_progress = progress;
                         }
                     }
@@ -2,15 +2,16 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/06/2017
-Last Modified: 12/10/2018
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_raster::*;
+use whitebox_common::structures::{EdgePolicy, Neighbourhood};
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to remove pits from a digital elevation model (DEM). Pits are single grid cells
@@ -41,7 +42,7 @@ impl BreachSingleCellPits {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -119,42 +120,11 @@ impl WhiteboxTool for BreachSingleCellPits {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i"
-                || vec[0].to_lowercase() == "--input"
-                || vec[0].to_lowercase() == "--dem"
-            {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -167,17 +137,11 @@ impl WhiteboxTool for BreachSingleCellPits {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -196,41 +160,41 @@ impl WhiteboxTool for BreachSingleCellPits {
             Ok(()) => (),
         }
 
-        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
-        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
-        let dx2 = [2, 2, 2, 2, 2, 1, 0, -1, -2, -2, -2, -2, -2, -1, 0, 1];
-        let dy2 = [-2, -1, 0, 1, 2, 2, 2, 2, 2, 1, 0, -1, -2, -2, -2, -2];
+        let d8 = Neighbourhood::d8();
+        let ring2 = Neighbourhood::ring(2);
+        // the D8 neighbour lying between the pit and each cell of the outer ring
         let breachcell = [0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 0];
         let (mut z, mut zn): (f64, f64);
         let mut flag: bool;
         for row in 0..rows {
             for col in 0..columns {
                 z = input[(row, col)];
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     flag = true;
-                    for i in 0..8 {
-                        zn = input[(row + dy[i], col + dx[i])];
-                        if zn < z && zn != nodata {
+                    for (_, r, c) in d8.iter_at(row, col, rows, columns, EdgePolicy::Skip) {
+                        zn = input[(r, c)];
+                        if zn < z && !is_nodata(zn, nodata) {
                             flag = false;
                             break;
                         }
                     }
                     if flag {
                         // it's a pit cell
-                        for i in 0..16 {
-                            zn = input[(row + dy2[i], col + dx2[i])];
-                            if zn < z && zn != nodata {
-                                output[(row + dy[breachcell[i]], col + dx[breachcell[i]])] =
-                                    (z + zn) / 2f64;
+                        for (i, r, c) in ring2.iter_at(row, col, rows, columns, EdgePolicy::Skip) {
+                            zn = input[(r, c)];
+                            if zn < z && !is_nodata(zn, nodata) {
+                                let n = breachcell[i];
+                                output[(row + d8.dy[n], col + d8.dx[n])] = (z + zn) / 2f64;
                             }
                         }
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
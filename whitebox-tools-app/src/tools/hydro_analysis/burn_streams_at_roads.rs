@@ -163,67 +163,14 @@ impl WhiteboxTool for BurnStreamsAtRoads {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut streams_file = String::new();
-        let mut roads_file = String::new();
-        let mut dem_file = String::new();
-        let mut output_file = String::new();
-        let mut road_width = 0f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-dem" {
-                dem_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-streams" {
-                streams_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-roads" {
-                roads_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-width" {
-                road_width = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut roads_file = parsed.get_string("roads").unwrap_or_default();
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let road_width = parsed.get_f64("width").unwrap_or(0f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -236,23 +183,13 @@ impl WhiteboxTool for BurnStreamsAtRoads {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !dem_file.contains(&sep) && !dem_file.contains("/") {
-            dem_file = format!("{}{}", working_directory, dem_file);
-        }
-        if !roads_file.contains(&sep) && !roads_file.contains("/") {
-            roads_file = format!("{}{}", working_directory, roads_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        roads_file = resolve_input_path(&roads_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading streams and roads data...")
@@ -406,10 +343,11 @@ impl WhiteboxTool for BurnStreamsAtRoads {
             }
 
             count += 1f64;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * count / (streams.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Rasterizing Streams: {}%", progress);
+                    reporter.progress("Rasterizing Streams", progress);
                     old_progress = progress;
                 }
             }
@@ -597,10 +535,11 @@ impl WhiteboxTool for BurnStreamsAtRoads {
             }
 
             count += 1f64;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * count / (roads.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding road intersections: {}%", progress);
+                    reporter.progress("Finding road intersections", progress);
                     old_progress = progress;
                 }
             }
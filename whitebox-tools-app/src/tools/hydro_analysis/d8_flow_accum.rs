@@ -9,14 +9,12 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool is used to generate a flow accumulation grid (i.e. catchment area) using the
 /// D8 (O'Callaghan and Mark, 1984) algorithm. This algorithm is an example of single-flow-direction
@@ -33,7 +31,7 @@ use std::thread;
 /// In addition to the input DEM/pointer, the user must specify the output type. The output flow-accumulation
 /// can be 1) `cells` (i.e. the number of inflowing grid cells), `catchment area` (i.e. the upslope area),
 /// or `specific contributing area` (i.e. the catchment area divided by the flow width. The default value
-/// is `cells`. The user must also specify whether the output flow-accumulation grid should be
+/// is `specific contributing area`. The user must also specify whether the output flow-accumulation grid should be
 /// log-tranformed (`--log`), i.e. the output, if this option is selected, will be the natural-logarithm of the
 /// accumulated flow value. This is a transformation that is often performed to better visualize the
 /// contributing area distribution. Because contributing areas tend to be very high along valley bottoms
@@ -72,7 +70,7 @@ impl D8FlowAccumulation {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM or D8 Pointer File".to_owned(),
-            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            flags: vec!["--dem".to_owned(), "-i".to_owned(), "--input".to_owned()],
             description: "Input raster DEM or D8 pointer file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -91,9 +89,9 @@ impl D8FlowAccumulation {
         parameters.push(ToolParameter{
             name: "Output Type".to_owned(), 
             flags: vec!["--out_type".to_owned()], 
-            description: "Output type; one of 'cells' (default), 'catchment area', and 'specific contributing area'.".to_owned(),
+            description: "Output type; one of 'cells', 'catchment area', and 'specific contributing area' (default).".to_owned(),
             parameter_type: ParameterType::OptionList(vec!["cells".to_owned(), "catchment area".to_owned(), "specific contributing area".to_owned()]),
-            default_value: Some("cells".to_owned()),
+            default_value: Some("specific contributing area".to_owned()),
             optional: true
         });
 
@@ -126,7 +124,7 @@ impl D8FlowAccumulation {
 
         parameters.push(ToolParameter {
             name: "If a pointer is input, does it use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "Input D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -192,75 +190,31 @@ impl WhiteboxTool for D8FlowAccumulation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut out_type = String::from("sca");
-        let mut log_transform = false;
-        let mut clip_max = false;
-        let mut pntr_input = false;
+        let log_transform = parsed.get_bool("log");
+        let clip_max = parsed.get_bool("clip");
+        let mut pntr_input = parsed.get_bool("pntr");
         let mut esri_style = false;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-out_type" {
-                if keyval {
-                    out_type = vec[1].to_lowercase();
-                } else {
-                    out_type = args[i + 1].to_lowercase();
-                }
-                if out_type.contains("specific") || out_type.contains("sca") {
-                    out_type = String::from("sca");
-                } else if out_type.contains("cells") {
-                    out_type = String::from("cells");
-                } else {
-                    out_type = String::from("ca");
-                }
-            } else if flag_val == "-log" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    log_transform = true;
-                }
-            } else if flag_val == "-clip" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    clip_max = true;
-                }
-            } else if flag_val == "-pntr" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    pntr_input = true;
-                }
-            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                    pntr_input = true;
-                }
+        if let Some(value) = parsed.get_string("out_type") {
+            out_type = value.to_lowercase();
+            if out_type.contains("specific") || out_type.contains("sca") {
+                out_type = String::from("sca");
+            } else if out_type.contains("cells") {
+                out_type = String::from("cells");
+            } else {
+                out_type = String::from("ca");
             }
         }
+        if parsed.get_bool("esri_pntr") {
+            esri_style = true;
+            pntr_input = true;
+        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -273,17 +227,11 @@ impl WhiteboxTool for D8FlowAccumulation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -302,12 +250,9 @@ impl WhiteboxTool for D8FlowAccumulation {
         // -2 indicates NoData, -1 indicates no downslope neighbour, 0-7 indicate flow to one neighbour.
         let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -2, -2)?;
         let mut interior_pit_found = false;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let pool = whitebox_common::utils::ThreadPool::new(&configs);
+        let num_procs = pool.num_threads() as isize;
 
         if !pntr_input {
             // calculate the flow direction from the input DEM
@@ -315,7 +260,7 @@ impl WhiteboxTool for D8FlowAccumulation {
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                pool.spawn(tid as usize, move || {
                     // let nodata = input.configs.nodata;
                     let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                     let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
@@ -338,13 +283,13 @@ impl WhiteboxTool for D8FlowAccumulation {
                         let mut data: Vec<i8> = vec![-2i8; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 dir = 0i8;
                                 max_slope = f64::MIN;
                                 neighbouring_nodata = false;
                                 for i in 0..8 {
                                     z_n = input[(row + dy[i], col + dx[i])];
-                                    if z_n != nodata {
+                                    if !is_nodata(z_n, nodata) {
                                         slope = (z - z_n) / grid_lengths[i];
                                         if slope > max_slope && slope > 0f64 {
                                             max_slope = slope;
@@ -364,7 +309,9 @@ impl WhiteboxTool for D8FlowAccumulation {
                                 }
                             }
                         }
-                        tx.send((row, data, interior_pit_found)).unwrap();
+                        if tx.send((row, data, interior_pit_found)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -375,10 +322,11 @@ impl WhiteboxTool for D8FlowAccumulation {
                 if pit {
                     interior_pit_found = true;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Flow directions: {}%", progress);
+                        reporter.progress("Flow directions", progress);
                         old_progress = progress;
                     }
                 }
@@ -390,7 +338,7 @@ impl WhiteboxTool for D8FlowAccumulation {
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                pool.spawn(tid as usize, move || {
                     // let nodata = input.configs.nodata;
                     let mut z: f64;
                     let mut interior_pit_found = false;
@@ -429,7 +377,7 @@ impl WhiteboxTool for D8FlowAccumulation {
                         let mut data: Vec<i8> = vec![-2i8; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 if z > 0f64 {
                                     data[col as usize] = pntr_matches[z as usize];
                                 } else {
@@ -437,7 +385,7 @@ impl WhiteboxTool for D8FlowAccumulation {
                                     // is this no-flow cell interior?
                                     neighbouring_nodata = false;
                                     for i in 0..8 {
-                                        if input.get_value(row + dy[i], col + dx[i]) == nodata {
+                                        if is_nodata(input.get_value(row + dy[i], col + dx[i]), nodata) {
                                             neighbouring_nodata = true;
                                             break;
                                         }
@@ -448,7 +396,9 @@ impl WhiteboxTool for D8FlowAccumulation {
                                 }
                             }
                         }
-                        tx.send((row, data, interior_pit_found)).unwrap();
+                        if tx.send((row, data, interior_pit_found)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -459,10 +409,11 @@ impl WhiteboxTool for D8FlowAccumulation {
                 if pit {
                     interior_pit_found = true;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Flow directions: {}%", progress);
+                        reporter.progress("Flow directions", progress);
                         old_progress = progress;
                     }
                 }
@@ -486,7 +437,7 @@ impl WhiteboxTool for D8FlowAccumulation {
             // let input = input.clone();
             let flow_dir = flow_dir.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            pool.spawn(tid as usize, move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let inflowing_vals: [i8; 8] = [4, 5, 6, 7, 0, 1, 2, 3];
@@ -510,7 +461,9 @@ impl WhiteboxTool for D8FlowAccumulation {
                             data[col as usize] = -1i8;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -528,10 +481,11 @@ impl WhiteboxTool for D8FlowAccumulation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -560,11 +514,12 @@ impl WhiteboxTool for D8FlowAccumulation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow accumulation: {}%", progress);
+                    reporter.progress("Flow accumulation", progress);
                     old_progress = progress;
                 }
             }
@@ -622,10 +577,11 @@ impl WhiteboxTool for D8FlowAccumulation {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
@@ -654,10 +610,11 @@ impl WhiteboxTool for D8FlowAccumulation {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
@@ -12,14 +12,13 @@ Notes: Assumes that each of the three input rasters have the same number of rows
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to perform a mass flux calculation using DEM-based surface flow-routing techniques.
 /// For example, it could be used to model the distribution of sediment or phosphorous within a catchment.
@@ -59,7 +58,7 @@ impl D8MassFlux {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["--dem".to_owned()],
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -160,61 +159,14 @@ impl WhiteboxTool for D8MassFlux {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut loading_file = String::new();
-        let mut efficiency_file = String::new();
-        let mut absorption_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-loading" {
-                if keyval {
-                    loading_file = vec[1].to_string();
-                } else {
-                    loading_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-efficiency" {
-                if keyval {
-                    efficiency_file = vec[1].to_string();
-                } else {
-                    efficiency_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-absorption" {
-                if keyval {
-                    absorption_file = vec[1].to_string();
-                } else {
-                    absorption_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut loading_file = parsed.get_string("loading").unwrap_or_default();
+        let mut efficiency_file = parsed.get_string("efficiency").unwrap_or_default();
+        let mut absorption_file = parsed.get_string("absorption").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -227,26 +179,14 @@ impl WhiteboxTool for D8MassFlux {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !loading_file.contains(&sep) && !loading_file.contains("/") {
-            loading_file = format!("{}{}", working_directory, loading_file);
-        }
-        if !efficiency_file.contains(&sep) && !efficiency_file.contains("/") {
-            efficiency_file = format!("{}{}", working_directory, efficiency_file);
-        }
-        if !absorption_file.contains(&sep) && !absorption_file.contains("/") {
-            absorption_file = format!("{}{}", working_directory, absorption_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        loading_file = resolve_input_path(&loading_file, working_directory)?;
+        efficiency_file = resolve_input_path(&efficiency_file, working_directory)?;
+        absorption_file = resolve_input_path(&absorption_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -268,8 +208,7 @@ impl WhiteboxTool for D8MassFlux {
         if efficiency.configs.rows as isize != rows
             || efficiency.configs.columns as isize != columns
         {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "All input images must share the same dimensions (rows and columns) and spatial extent."));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
         }
         let efficiency_multiplier = if efficiency.configs.maximum > 1f64 {
             0.01f64 // assumed to be percent...need proportion
@@ -281,22 +220,17 @@ impl WhiteboxTool for D8MassFlux {
         if absorption.configs.rows as isize != rows
             || absorption.configs.columns as isize != columns
         {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "All input images must share the same dimensions (rows and columns) and spatial extent."));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
         }
 
         let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let nodata = input.configs.nodata;
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
@@ -319,13 +253,13 @@ impl WhiteboxTool for D8MassFlux {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
                                 z_n = input[(row + dy[i], col + dx[i])];
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -347,7 +281,9 @@ impl WhiteboxTool for D8MassFlux {
                             data[col as usize] = -1i8;
                         }
                     }
-                    tx.send((row, data, interior_pit_found)).unwrap();
+                    if tx.send((row, data, interior_pit_found)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -359,10 +295,11 @@ impl WhiteboxTool for D8MassFlux {
             if pit {
                 interior_pit_found = true;
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow directions: {}%", progress);
+                    reporter.progress("Flow directions", progress);
                     old_progress = progress;
                 }
             }
@@ -377,7 +314,7 @@ impl WhiteboxTool for D8MassFlux {
             let input = input.clone();
             let flow_dir = flow_dir.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let inflowing_vals: [i8; 8] = [4, 5, 6, 7, 0, 1, 2, 3];
@@ -387,7 +324,7 @@ impl WhiteboxTool for D8MassFlux {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if flow_dir[(row + dy[i], col + dx[i])] == inflowing_vals[i] {
@@ -399,7 +336,9 @@ impl WhiteboxTool for D8MassFlux {
                             data[col as usize] = -1i8;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -417,10 +356,11 @@ impl WhiteboxTool for D8MassFlux {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -432,8 +372,7 @@ impl WhiteboxTool for D8MassFlux {
         // read in the loading file and initialize output with these data.
         let loading = Raster::new(&loading_file, "r")?; // the loading raster
         if loading.configs.rows as isize != rows || loading.configs.columns as isize != columns {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "All input images must share the same dimensions (rows and columns) and spatial extent."));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
         }
         let load_nodata = absorption.configs.nodata;
 
@@ -444,16 +383,17 @@ impl WhiteboxTool for D8MassFlux {
             for row in 0..rows {
                 for col in 0..columns {
                     load = loading.get_value(row, col);
-                    if load != load_nodata {
+                    if !is_nodata(load, load_nodata) {
                         output.set_value(row, col, load);
                     } else {
                         output.set_value(row, col, nodata);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Initializing output raster: {}%", progress);
+                        reporter.progress("Initializing output raster", progress);
                         old_progress = progress;
                     }
                 }
@@ -487,11 +427,12 @@ impl WhiteboxTool for D8MassFlux {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow accumulation: {}%", progress);
+                    reporter.progress("Flow accumulation", progress);
                     old_progress = progress;
                 }
             }
@@ -499,15 +440,16 @@ impl WhiteboxTool for D8MassFlux {
 
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) == nodata {
+                if is_nodata(input.get_value(row, col), nodata) {
                     output.set_value(row, col, nodata);
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Correcting values: {}%", progress);
+                    reporter.progress("Correcting values", progress);
                     old_progress = progress;
                 }
             }
@@ -2,20 +2,20 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 16/06/2017
-Last Modified: 18/10/2019
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
+use std::ops::Range;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 use whitebox_common::structures::Array2D;
 
 /// This tool is used to generate a flow pointer grid using the simple D8 (O'Callaghan and Mark, 1984) algorithm. The
@@ -40,7 +40,10 @@ use whitebox_common::structures::Array2D;
 /// Grid cells possessing the NoData value in the input DEM are assigned the NoData value in the output image.
 ///
 /// # Memory Usage
-/// The peak memory usage of this tool is approximately 10 bytes per grid cell.
+/// The peak memory usage of this tool is approximately 10 bytes per grid cell. When the `tile_cache_mb`
+/// setting is non-zero and the input DEM is a Whitebox raster that is too large to fit within the tile
+/// cache, the tool instead processes the DEM out of core, one band of rows at a time, and memory usage
+/// is bounded by the size of the cache. The output must then also be a Whitebox raster.
 /// 
 /// # Reference
 /// O'Callaghan, J. F., & Mark, D. M. (1984). The extraction of drainage networks from digital elevation data.
@@ -66,7 +69,7 @@ impl D8Pointer {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -84,7 +87,7 @@ impl D8Pointer {
 
         parameters.push(ToolParameter {
             name: "Should the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -153,50 +156,12 @@ impl WhiteboxTool for D8Pointer {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i"
-                || vec[0].to_lowercase() == "--input"
-                || vec[0].to_lowercase() == "--dem"
-            {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-esri_pntr"
-                || vec[0].to_lowercase() == "--esri_pntr"
-                || vec[0].to_lowercase() == "--esri_style"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let esri_style = parsed.get_bool("esri_pntr");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -209,106 +174,50 @@ impl WhiteboxTool for D8Pointer {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
         };
 
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+
+        if TiledRaster::is_out_of_core_input(&input_file) {
+            return self.run_out_of_core(
+                &input_file,
+                &output_file,
+                esri_style,
+                num_procs,
+                verbose,
+                reporter,
+            );
+        }
+
         let input = Arc::new(Raster::new(&input_file, "r")?);
 
         let start = Instant::now();
-        let cell_size_x = input.configs.resolution_x;
-        let cell_size_y = input.configs.resolution_y;
-        let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
-
-        // let mut output = Raster::initialize_using_file(&output_file, &input);
         let rows = input.configs.rows as isize;
-        let nodata = input.configs.nodata;
         let out_nodata = -32768i16;
         let columns = input.configs.columns as isize;
 
-        let mut num_procs = num_cpus::get() as isize;
-        let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
         let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
-                let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
-                let grid_lengths = [
-                    diag_cell_size,
-                    cell_size_x,
-                    diag_cell_size,
-                    cell_size_y,
-                    diag_cell_size,
-                    cell_size_x,
-                    diag_cell_size,
-                    cell_size_y,
-                ];
-                // let out_vals = match esri_style {
-                //     true => [128f64, 1f64, 2f64, 4f64, 8f64, 16f64, 32f64, 64f64],
-                //     false => [1f64, 2f64, 4f64, 8f64, 16f64, 32f64, 64f64, 128f64],
-                // };
-                let out_vals = match esri_style {
-                    true => [128i16, 1, 2, 4, 8, 16, 32, 64],
-                    false => [1i16, 2, 4, 8, 16, 32, 64, 128],
-                };
-                let (mut z, mut z_n, mut slope): (f64, f64, f64);
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    // let mut data = vec![out_nodata; columns as usize];
-                    let mut data = vec![out_nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
-                            let mut dir = 0;
-                            let mut max_slope = f64::MIN;
-                            for i in 0..8 {
-                                z_n = input[(row + d_y[i], col + d_x[i])];
-                                if z_n != nodata {
-                                    slope = (z - z_n) / grid_lengths[i];
-                                    if slope > max_slope && slope > 0f64 {
-                                        max_slope = slope;
-                                        dir = i;
-                                    }
-                                }
-                            }
-                            if max_slope >= 0f64 {
-                                data[col as usize] = out_vals[dir]; //(1 << dir) as f64;
-                            } else {
-                                data[col as usize] = 0i16; // 0f64;
-                            }
-                        }
-                    }
-                    tx1.send((row, data)).unwrap();
-                }
-            });
-        }
+        spawn_pointer_workers(&input, 0..rows, esri_style, num_procs, &tx);
 
         let mut output: Array2D<i16> = Array2D::new(rows, columns, out_nodata, out_nodata)?;
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -357,3 +266,162 @@ impl WhiteboxTool for D8Pointer {
         Ok(())
     }
 }
+
+impl D8Pointer {
+    /// Calculates the pointers of a raster that is too large to read into memory, one band of
+    /// rows at a time.
+    fn run_out_of_core(
+        &self,
+        input_file: &str,
+        output_file: &str,
+        esri_style: bool,
+        num_procs: isize,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        if verbose {
+            println!("Processing the input raster out of core...")
+        };
+        let mut input = TiledRaster::open(input_file)?;
+        let start = Instant::now();
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns;
+        let out_nodata = -32768i16;
+
+        let mut output = TiledRaster::initialize_using_config(output_file, &input.configs)?;
+        output.configs.nodata = out_nodata as f64;
+        output.configs.data_type = DataType::I16;
+        output.configs.palette = "qual.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        let band_rows = input.tile_size as isize;
+        let mut band_start = 0isize;
+        let mut rows_completed = 0isize;
+        while band_start < rows {
+            let n = band_rows.min(rows - band_start);
+            // the band is padded by a row on either side, which holds the neighbours of its edge cells
+            let band = Arc::new(input.read_rows(band_start - 1, n as usize + 2)?);
+            let (tx, rx) = mpsc::channel();
+            spawn_pointer_workers(&band, 1..n + 1, esri_style, num_procs, &tx);
+            let mut data = vec![out_nodata as f64; n as usize * columns];
+            for _ in 0..n {
+                let (row, values) = rx.recv().expect("Error receiving data from thread.");
+                let offset = (row - 1) as usize * columns;
+                for col in 0..columns {
+                    data[offset + col] = values[col] as f64;
+                }
+
+                reporter.check_cancelled()?;
+                rows_completed += 1;
+                if verbose {
+                    progress = (100.0_f64 * rows_completed as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Progress", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            output.set_rows(band_start, &data)?;
+            band_start += n;
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("ESRI-style output: {}", esri_style));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        output.write()?;
+        if verbose {
+            println!("Output file written");
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Spawns threads that calculate the pointers of the cells in `rows` of `input`, sending each
+/// row of output values, along with its row number, to `tx`.
+fn spawn_pointer_workers(
+    input: &Arc<Raster>,
+    rows: Range<isize>,
+    esri_style: bool,
+    num_procs: isize,
+    tx: &mpsc::Sender<(isize, Vec<i16>)>,
+) {
+    let cell_size_x = input.configs.resolution_x;
+    let cell_size_y = input.configs.resolution_y;
+    let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+    let nodata = input.configs.nodata;
+    let out_nodata = -32768i16;
+    let columns = input.configs.columns as isize;
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let rows = rows.clone();
+        let tx1 = tx.clone();
+        spawn(move || {
+            let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+            let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+            let grid_lengths = [
+                diag_cell_size,
+                cell_size_x,
+                diag_cell_size,
+                cell_size_y,
+                diag_cell_size,
+                cell_size_x,
+                diag_cell_size,
+                cell_size_y,
+            ];
+            // let out_vals = match esri_style {
+            //     true => [128f64, 1f64, 2f64, 4f64, 8f64, 16f64, 32f64, 64f64],
+            //     false => [1f64, 2f64, 4f64, 8f64, 16f64, 32f64, 64f64, 128f64],
+            // };
+            let out_vals = match esri_style {
+                true => [128i16, 1, 2, 4, 8, 16, 32, 64],
+                false => [1i16, 2, 4, 8, 16, 32, 64, 128],
+            };
+            let (mut z, mut z_n, mut slope): (f64, f64, f64);
+            for row in rows.filter(|r| r % num_procs == tid) {
+                // let mut data = vec![out_nodata; columns as usize];
+                let mut data = vec![out_nodata; columns as usize];
+                for col in 0..columns {
+                    z = input[(row, col)];
+                    if !is_nodata(z, nodata) {
+                        let mut dir = 0;
+                        let mut max_slope = f64::MIN;
+                        for i in 0..8 {
+                            z_n = input[(row + d_y[i], col + d_x[i])];
+                            if !is_nodata(z_n, nodata) {
+                                slope = (z - z_n) / grid_lengths[i];
+                                if slope > max_slope && slope > 0f64 {
+                                    max_slope = slope;
+                                    dir = i;
+                                }
+                            }
+                        }
+                        if max_slope >= 0f64 {
+                            data[col as usize] = out_vals[dir]; //(1 << dir) as f64;
+                        } else {
+                            data[col as usize] = 0i16; // 0f64;
+                        }
+                    }
+                }
+                if tx1.send((row, data)).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+}
@@ -9,15 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool is used to generate a flow accumulation grid (i.e. contributing area) using the D-infinity algorithm
 /// (Tarboton, 1997). This algorithm is an examples of a multiple-flow-direction (MFD) method because the flow entering
@@ -68,7 +66,7 @@ impl DInfFlowAccumulation {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM or D-inf Pointer File".to_owned(),
-            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            flags: vec!["--dem".to_owned(), "-i".to_owned(), "--input".to_owned()],
             description: "Input raster DEM or D-infinity pointer file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -195,80 +193,25 @@ impl WhiteboxTool for DInfFlowAccumulation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut out_type = String::from("sca");
-        let mut convergence_threshold = f64::INFINITY;
-        let mut log_transform = false;
-        let mut clip_max = false;
-        let mut pntr_input = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-out_type" {
-                if keyval {
-                    out_type = vec[1].to_lowercase();
-                } else {
-                    out_type = args[i + 1].to_lowercase();
-                }
-                if out_type.contains("specific") || out_type.contains("sca") {
-                    out_type = String::from("sca");
-                } else if out_type.contains("cells") {
-                    out_type = String::from("cells");
-                } else {
-                    out_type = String::from("ca");
-                }
-            } else if flag_val == "-threshold" {
-                if keyval {
-                    convergence_threshold = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    convergence_threshold = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-log" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    log_transform = true;
-                }
-            } else if flag_val == "-clip" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    clip_max = true;
-                }
-            } else if flag_val == "-pntr" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    pntr_input = true;
-                }
+        let convergence_threshold = parsed.get_f64("threshold").unwrap_or(f64::INFINITY);
+        let log_transform = parsed.get_bool("log");
+        let clip_max = parsed.get_bool("clip");
+        let pntr_input = parsed.get_bool("pntr");
+
+        if let Some(value) = parsed.get_string("out_type") {
+            out_type = value.to_lowercase();
+            if out_type.contains("specific") || out_type.contains("sca") {
+                out_type = String::from("sca");
+            } else if out_type.contains("cells") {
+                out_type = String::from("cells");
+            } else {
+                out_type = String::from("ca");
             }
         }
 
@@ -283,17 +226,11 @@ impl WhiteboxTool for DInfFlowAccumulation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -313,19 +250,16 @@ impl WhiteboxTool for DInfFlowAccumulation {
         // calculate the flow directions
         let mut flow_dir: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
         let mut interior_pit_found = false;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let pool = whitebox_common::utils::ThreadPool::new(&configs);
+        let num_procs = pool.num_threads() as isize;
 
         if !pntr_input {
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                pool.spawn(tid as usize, move || {
                     let nodata = input.configs.nodata;
                     let grid_res = (cell_size_x + cell_size_y) / 2.0;
                     let mut dir: f64;
@@ -360,7 +294,7 @@ impl WhiteboxTool for DInfFlowAccumulation {
                         let mut data: Vec<f64> = vec![nodata; columns as usize];
                         for col in 0..columns {
                             e0 = input[(row, col)];
-                            if e0 != nodata {
+                            if !is_nodata(e0, nodata) {
                                 dir = 360.0;
                                 max_slope = f64::MIN;
                                 neighbouring_nodata = false;
@@ -369,7 +303,7 @@ impl WhiteboxTool for DInfFlowAccumulation {
                                     af = af_vals[i];
                                     e1 = input[(row + e1_row[i], col + e1_col[i])];
                                     e2 = input[(row + e2_row[i], col + e2_col[i])];
-                                    if e1 != nodata && e2 != nodata {
+                                    if !is_nodata(e1, nodata) && !is_nodata(e2, nodata) {
                                         if e0 > e1 && e0 > e2 {
                                             s1 = (e0 - e1) / grid_res;
                                             s2 = (e1 - e2) / grid_res;
@@ -435,7 +369,9 @@ impl WhiteboxTool for DInfFlowAccumulation {
                                 data[col as usize] = -1f64;
                             }
                         }
-                        tx.send((row, data, interior_pit_found)).unwrap();
+                        if tx.send((row, data, interior_pit_found)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -446,10 +382,11 @@ impl WhiteboxTool for DInfFlowAccumulation {
                 if pit {
                     interior_pit_found = true;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Flow directions: {}%", progress);
+                        reporter.progress("Flow directions", progress);
                         old_progress = progress;
                     }
                 }
@@ -466,7 +403,7 @@ impl WhiteboxTool for DInfFlowAccumulation {
         for tid in 0..num_procs {
             let flow_dir = flow_dir.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            pool.spawn(tid as usize, move || {
                 let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
                 let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let start_fd = [180f64, 225f64, 270f64, 315f64, 0f64, 45f64, 90f64, 135f64];
@@ -477,7 +414,7 @@ impl WhiteboxTool for DInfFlowAccumulation {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         dir = flow_dir[(row, col)];
-                        if dir != nodata {
+                        if !is_nodata(dir, nodata) {
                             count = 0;
                             for i in 0..8 {
                                 dir = flow_dir[(row + d_y[i], col + d_x[i])];
@@ -497,7 +434,9 @@ impl WhiteboxTool for DInfFlowAccumulation {
                             data[col as usize] = count;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -517,10 +456,11 @@ impl WhiteboxTool for DInfFlowAccumulation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -637,11 +577,12 @@ impl WhiteboxTool for DInfFlowAccumulation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow accumulation: {}%", progress);
+                    reporter.progress("Flow accumulation", progress);
                     old_progress = progress;
                 }
             }
@@ -659,17 +600,18 @@ impl WhiteboxTool for DInfFlowAccumulation {
         if log_transform {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
+                    if is_nodata(input[(row, col)], nodata) {
                         output[(row, col)] = nodata;
                     } else {
                         output[(row, col)] = (output[(row, col)] * cell_area / avg_cell_size).ln();
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
@@ -677,17 +619,18 @@ impl WhiteboxTool for DInfFlowAccumulation {
         } else {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
+                    if is_nodata(input[(row, col)], nodata) {
                         output[(row, col)] = nodata;
                     } else {
                         output[(row, col)] = output[(row, col)] * cell_area / avg_cell_size;
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
@@ -12,15 +12,14 @@ Notes: Assumes that each of the three input rasters have the same number of rows
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to perform a mass flux calculation using DEM-based surface flow-routing techniques. For
 /// example, it could be used to model the distribution of sediment or phosphorous within a catchment. Flow-routing
@@ -60,7 +59,7 @@ impl DInfMassFlux {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["--dem".to_owned()],
+            flags: vec!["-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -161,61 +160,14 @@ impl WhiteboxTool for DInfMassFlux {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut loading_file = String::new();
-        let mut efficiency_file = String::new();
-        let mut absorption_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-loading" {
-                if keyval {
-                    loading_file = vec[1].to_string();
-                } else {
-                    loading_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-efficiency" {
-                if keyval {
-                    efficiency_file = vec[1].to_string();
-                } else {
-                    efficiency_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-absorption" {
-                if keyval {
-                    absorption_file = vec[1].to_string();
-                } else {
-                    absorption_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut loading_file = parsed.get_string("loading").unwrap_or_default();
+        let mut efficiency_file = parsed.get_string("efficiency").unwrap_or_default();
+        let mut absorption_file = parsed.get_string("absorption").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -228,26 +180,14 @@ impl WhiteboxTool for DInfMassFlux {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !loading_file.contains(&sep) && !loading_file.contains("/") {
-            loading_file = format!("{}{}", working_directory, loading_file);
-        }
-        if !efficiency_file.contains(&sep) && !efficiency_file.contains("/") {
-            efficiency_file = format!("{}{}", working_directory, efficiency_file);
-        }
-        if !absorption_file.contains(&sep) && !absorption_file.contains("/") {
-            absorption_file = format!("{}{}", working_directory, absorption_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        loading_file = resolve_input_path(&loading_file, working_directory)?;
+        efficiency_file = resolve_input_path(&efficiency_file, working_directory)?;
+        absorption_file = resolve_input_path(&absorption_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -268,8 +208,7 @@ impl WhiteboxTool for DInfMassFlux {
         if efficiency.configs.rows as isize != rows
             || efficiency.configs.columns as isize != columns
         {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "All input images must share the same dimensions (rows and columns) and spatial extent."));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
         }
         let efficiency_multiplier = if efficiency.configs.maximum > 1f64 {
             0.01f64 // assumpted to be percent...need proportion
@@ -281,24 +220,19 @@ impl WhiteboxTool for DInfMassFlux {
         if absorption.configs.rows as isize != rows
             || absorption.configs.columns as isize != columns
         {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "All input images must share the same dimensions (rows and columns) and spatial extent."));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
         }
 
         // calculate the flow directions
         let mut flow_dir: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let nodata = input.configs.nodata;
                 let grid_res = (cell_size_x + cell_size_y) / 2.0;
                 let mut dir: f64;
@@ -325,7 +259,7 @@ impl WhiteboxTool for DInfMassFlux {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         e0 = input[(row, col)];
-                        if e0 != nodata {
+                        if !is_nodata(e0, nodata) {
                             dir = 360.0;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
@@ -334,7 +268,7 @@ impl WhiteboxTool for DInfMassFlux {
                                 af = af_vals[i];
                                 e1 = input[(row + e1_row[i], col + e1_col[i])];
                                 e2 = input[(row + e2_row[i], col + e2_col[i])];
-                                if e1 != nodata && e2 != nodata {
+                                if !is_nodata(e1, nodata) && !is_nodata(e2, nodata) {
                                     if e0 > e1 && e0 > e2 {
                                         s1 = (e0 - e1) / grid_res;
                                         if s1 == 0.0 {
@@ -403,7 +337,9 @@ impl WhiteboxTool for DInfMassFlux {
                             data[col as usize] = -1f64;
                         }
                     }
-                    tx.send((row, data, interior_pit_found)).unwrap();
+                    if tx.send((row, data, interior_pit_found)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -415,10 +351,11 @@ impl WhiteboxTool for DInfMassFlux {
             if pit {
                 interior_pit_found = true;
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow directions: {}%", progress);
+                    reporter.progress("Flow directions", progress);
                     old_progress = progress;
                 }
             }
@@ -431,7 +368,7 @@ impl WhiteboxTool for DInfMassFlux {
         for tid in 0..num_procs {
             let flow_dir = flow_dir.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
                 let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let start_fd = [180f64, 225f64, 270f64, 315f64, 0f64, 45f64, 90f64, 135f64];
@@ -442,7 +379,7 @@ impl WhiteboxTool for DInfMassFlux {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         dir = flow_dir[(row, col)];
-                        if dir != nodata {
+                        if !is_nodata(dir, nodata) {
                             count = 0;
                             for i in 0..8 {
                                 dir = flow_dir[(row + d_y[i], col + d_x[i])];
@@ -462,7 +399,9 @@ impl WhiteboxTool for DInfMassFlux {
                             data[col as usize] = count;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -480,10 +419,11 @@ impl WhiteboxTool for DInfMassFlux {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -495,8 +435,7 @@ impl WhiteboxTool for DInfMassFlux {
         // read in the loading file and initialize output with these data.
         let loading = Raster::new(&loading_file, "r")?; // the loading raster
         if loading.configs.rows as isize != rows || loading.configs.columns as isize != columns {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                "All input images must share the same dimensions (rows and columns) and spatial extent."));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
         }
         let load_nodata = absorption.configs.nodata;
 
@@ -511,16 +450,17 @@ impl WhiteboxTool for DInfMassFlux {
             for row in 0..rows {
                 for col in 0..columns {
                     load = loading.get_value(row, col);
-                    if load != load_nodata {
+                    if !is_nodata(load, load_nodata) {
                         output.set_value(row, col, load);
                     } else {
                         output.set_value(row, col, nodata);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Initializing output raster: {}%", progress);
+                        reporter.progress("Initializing output raster", progress);
                         old_progress = progress;
                     }
                 }
@@ -623,11 +563,12 @@ impl WhiteboxTool for DInfMassFlux {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow accumulation: {}%", progress);
+                    reporter.progress("Flow accumulation", progress);
                     old_progress = progress;
                 }
             }
@@ -635,15 +576,16 @@ impl WhiteboxTool for DInfMassFlux {
 
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) == nodata {
+                if is_nodata(input.get_value(row, col), nodata) {
                     output.set_value(row, col, nodata);
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Correcting values: {}%", progress);
+                    reporter.progress("Correcting values", progress);
                     old_progress = progress;
                 }
             }
@@ -9,16 +9,15 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::collections::VecDeque;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to calculate the distance from each grid cell in a raster to the nearest stream cell,
 /// measured along the downslope flowpath. The user must specify the name of an input digital elevation model (`--dem`)
@@ -78,7 +77,7 @@ impl DownslopeDistanceToStream {
 
         parameters.push(ToolParameter {
             name: "Use the D-infinity flow algorithm instead of D8?".to_owned(),
-            flags: vec!["--dinf".to_owned()],
+            flags: vec!["--dinfinity".to_owned(), "--dinf".to_owned()],
             description: "Use the D-infinity flow algorithm instead of D8?".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -143,52 +142,13 @@ impl WhiteboxTool for DownslopeDistanceToStream {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut dem_file = String::new();
-        let mut streams_file = String::new();
-        let mut output_file = String::new();
-        let mut use_dinf = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-dem" {
-                if keyval {
-                    dem_file = vec[1].to_string();
-                } else {
-                    dem_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-streams" {
-                if keyval {
-                    streams_file = vec[1].to_string();
-                } else {
-                    streams_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-dinf" || flag_val == "-dinfinity" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    use_dinf = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let use_dinf = parsed.get_bool("dinf");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -201,20 +161,12 @@ impl WhiteboxTool for DownslopeDistanceToStream {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !dem_file.contains(&sep) && !dem_file.contains("/") {
-            dem_file = format!("{}{}", working_directory, dem_file);
-        }
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading DEM data...")
@@ -243,27 +195,21 @@ impl WhiteboxTool for DownslopeDistanceToStream {
         if dem.configs.rows != streams.configs.rows
             || dem.configs.columns != streams.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&dem, &streams, verbose);
 
         if !use_dinf {
             /////////////////////////////////////////////
             // Perform the D8 flow pointer calculation //
             /////////////////////////////////////////////
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let dem = dem.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                     let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                     let grid_lengths = [
@@ -285,13 +231,13 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                         let mut data: Vec<i8> = vec![flow_nodata; columns as usize];
                         for col in 0..columns {
                             z = dem.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 dir = 0i8;
                                 max_slope = f64::MIN;
                                 neighbouring_nodata = false;
                                 for i in 0..8 {
                                     z_n = dem.get_value(row + dy[i], col + dx[i]);
-                                    if z_n != nodata {
+                                    if !is_nodata(z_n, nodata) {
                                         slope = (z - z_n) / grid_lengths[i];
                                         if slope > max_slope && slope > 0f64 {
                                             max_slope = slope;
@@ -311,7 +257,9 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                                 }
                             }
                         }
-                        tx.send((row, data, interior_pit_found)).unwrap();
+                        if tx.send((row, data, interior_pit_found)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -332,12 +280,12 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                 for col in 0..columns {
                     // stream cells get added to the stack; nodata cells get assigned that in the output
                     if streams.get_value(row, col) > 0f64
-                        && streams.get_value(row, col) != streams_nodata
+                        && !is_nodata(streams.get_value(row, col), streams_nodata)
                     {
                         output.set_value(row, col, 0f64);
                         stack.push((row, col, 0f64));
                     }
-                    if dem.get_value(row, col) == nodata {
+                    if is_nodata(dem.get_value(row, col), nodata) {
                         output.set_value(row, col, nodata);
                         num_solved_cells += 1;
                     }
@@ -349,10 +297,11 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Flow directions: {}%", progress);
+                        reporter.progress("Flow directions", progress);
                         old_progress = progress;
                     }
                 }
@@ -387,7 +336,7 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                     if flow_dir.get_value(row_n, col_n) == inflowing_vals[n]
                         && output.get_value(row_n, col_n) == background_value
                     {
-                        if stream_dist != nodata {
+                        if !is_nodata(stream_dist, nodata) {
                             dist = stream_dist + grid_lengths[n];
                             output.set_value(row_n, col_n, dist);
                             stack.push((row_n, col_n, dist));
@@ -397,12 +346,13 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     num_solved_cells += 1;
                     progress =
                         (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -446,18 +396,14 @@ impl WhiteboxTool for DownslopeDistanceToStream {
             // calculate the D-inf flow directions
             let mut flow_dir: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
             let mut interior_pit_found = false;
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let input = dem.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let nodata = input.configs.nodata;
                     let grid_res = (cell_size_x + cell_size_y) / 2.0;
                     let mut dir: f64;
@@ -492,7 +438,7 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                         let mut data: Vec<f64> = vec![nodata; columns as usize];
                         for col in 0..columns {
                             e0 = input[(row, col)];
-                            if e0 != nodata {
+                            if !is_nodata(e0, nodata) {
                                 dir = 360.0;
                                 max_slope = f64::MIN;
                                 neighbouring_nodata = false;
@@ -501,7 +447,7 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                                     af = af_vals[i];
                                     e1 = input[(row + e1_row[i], col + e1_col[i])];
                                     e2 = input[(row + e2_row[i], col + e2_col[i])];
-                                    if e1 != nodata && e2 != nodata {
+                                    if !is_nodata(e1, nodata) && !is_nodata(e2, nodata) {
                                         if e0 > e1 && e0 > e2 {
                                             s1 = (e0 - e1) / grid_res;
                                             s2 = (e1 - e2) / grid_res;
@@ -567,7 +513,9 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                                 data[col as usize] = -1f64;
                             }
                         }
-                        tx.send((row, data, interior_pit_found)).unwrap();
+                        if tx.send((row, data, interior_pit_found)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -578,10 +526,11 @@ impl WhiteboxTool for DownslopeDistanceToStream {
                 if pit {
                     interior_pit_found = true;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Flow directions: {}%", progress);
+                        reporter.progress("Flow directions", progress);
                         old_progress = progress;
                     }
                 }
@@ -595,9 +544,10 @@ impl WhiteboxTool for DownslopeDistanceToStream {
             let mut z: f64;
             let mut num_solved_cells = 0;
             for row in 0..rows {
+                reporter.check_cancelled()?;
                 for col in 0..columns {
                     z = dem.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         dir = flow_dir.get_value(row, col);
                         if dir != -1.0 {
                             if dir == 0.0
@@ -648,6 +598,7 @@ impl WhiteboxTool for DownslopeDistanceToStream {
             let mut dist: f64;
             let num_cells = rows * columns;
             while !queue.is_empty() {
+                reporter.check_cancelled()?;
                 let (row, column) = queue.pop_front().unwrap();
                 dist = output.get_value(row, column);
 
@@ -682,12 +633,13 @@ impl WhiteboxTool for DownslopeDistanceToStream {
 
                 progress = ((num_solved_cells as f64 / num_cells as f64) * 100.0) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
 
             for row in 0..rows {
+                reporter.check_cancelled()?;
                 for col in 0..columns {
                     if output.get_value(row, col) == f64::MAX {
                         output.set_value(row, col, nodata);
@@ -695,7 +647,7 @@ impl WhiteboxTool for DownslopeDistanceToStream {
 
                         progress = ((num_solved_cells as f64 / num_cells as f64) * 100.0) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
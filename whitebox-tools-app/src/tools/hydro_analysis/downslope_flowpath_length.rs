@@ -92,7 +92,7 @@ impl DownslopeFlowpathLength {
 
         parameters.push(ToolParameter {
             name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -158,59 +158,14 @@ impl WhiteboxTool for DownslopeFlowpathLength {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut d8_file = String::new();
-        let mut watersheds_file = String::new();
-        let mut weights_file = String::new();
-        let mut output_file = String::new();
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-d8_pntr" {
-                d8_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-watersheds" {
-                watersheds_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-weights" {
-                weights_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("d8_pntr").unwrap_or_default();
+        let mut watersheds_file = parsed.get_string("watersheds").unwrap_or_default();
+        let mut weights_file = parsed.get_string("weights").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let esri_style = parsed.get_bool("esri_pntr");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -223,32 +178,22 @@ impl WhiteboxTool for DownslopeFlowpathLength {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !d8_file.contains(&sep) && !d8_file.contains("/") {
-            d8_file = format!("{}{}", working_directory, d8_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        d8_file = resolve_input_path(&d8_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
         let use_watersheds: bool;
         if !watersheds_file.is_empty() {
             use_watersheds = true;
-            if !watersheds_file.contains(&sep) && !watersheds_file.contains("/") {
-                watersheds_file = format!("{}{}", working_directory, watersheds_file);
-            }
+            watersheds_file = resolve_input_path(&watersheds_file, working_directory)?;
         } else {
             use_watersheds = false;
         }
         let use_weights: bool;
         if !weights_file.is_empty() {
             use_weights = true;
-            if !weights_file.contains(&sep) {
-                weights_file = format!("{}{}", working_directory, weights_file);
-            }
+            weights_file = resolve_input_path(&weights_file, working_directory)?;
         } else {
             use_weights = false
         }
@@ -273,8 +218,7 @@ impl WhiteboxTool for DownslopeFlowpathLength {
                 // if verbose { println!("Reading watershed data...") };
                 let r = Raster::new(&watersheds_file, "r")?;
                 if r.configs.rows != rows as usize || r.configs.columns != columns as usize {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                        "The input files must have the same number of rows and columns and spatial extent."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
                 }
                 r.get_data_as_f32_array2d()
             }
@@ -290,8 +234,7 @@ impl WhiteboxTool for DownslopeFlowpathLength {
                 // if verbose { println!("Reading weights data...") };
                 let r = Raster::new(&weights_file, "r")?;
                 if r.configs.rows != rows as usize || r.configs.columns != columns as usize {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                        "The input files must have the same number of rows and columns and spatial extent."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
                 }
                 r.get_data_as_f32_array2d()
             }
@@ -351,7 +294,7 @@ impl WhiteboxTool for DownslopeFlowpathLength {
         for row in 0..rows {
             for col in 0..columns {
                 current_id = watersheds.get_value(row, col) as f64;
-                if pntr.get_value(row, col) >= 0.0 && pntr.get_value(row, col) != nodata && current_id > 0f64 {
+                if pntr.get_value(row, col) >= 0.0 && !is_nodata(pntr.get_value(row, col), nodata) && current_id > 0f64 {
                     dist = 0f64;
                     flag = false;
                     x = col;
@@ -359,7 +302,7 @@ impl WhiteboxTool for DownslopeFlowpathLength {
                     while !flag {
                         // find its downslope neighbour
                         dir = pntr.get_value(y, x);
-                        if dir > 0f64 && dir != nodata {
+                        if dir > 0f64 && !is_nodata(dir, nodata) {
                             if dir > 128f64 || pntr_matches[dir as usize] == 999 {
                                 return Err(Error::new(ErrorKind::InvalidInput,
                                     "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools."));
@@ -390,7 +333,7 @@ impl WhiteboxTool for DownslopeFlowpathLength {
 
                         // find its downslope neighbour
                         dir = pntr.get_value(y, x);
-                        if dir > 0f64 && dir != nodata {
+                        if dir > 0f64 && !is_nodata(dir, nodata) {
                             // move x and y accordingly
                             c = pntr_matches[dir as usize];
                             x += dx[c];
@@ -412,10 +355,11 @@ impl WhiteboxTool for DownslopeFlowpathLength {
                     output.set_value(row, col, out_nodata);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
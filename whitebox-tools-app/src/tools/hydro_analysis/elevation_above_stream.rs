@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to calculate the elevation of each grid cell in a raster above the nearest stream cell,
 /// measured along the downslope flowpath. This terrain index, a measure of relative topographic position, is
@@ -141,47 +140,12 @@ impl WhiteboxTool for ElevationAboveStream {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut dem_file = String::new();
-        let mut streams_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-dem" || flag_val == "-i" {
-                dem_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-streams" {
-                streams_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -194,20 +158,12 @@ impl WhiteboxTool for ElevationAboveStream {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !dem_file.contains(&sep) && !dem_file.contains("/") {
-            dem_file = format!("{}{}", working_directory, dem_file);
-        }
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading DEM data...")
@@ -236,23 +192,17 @@ impl WhiteboxTool for ElevationAboveStream {
         if dem.configs.rows != streams.configs.rows
             || dem.configs.columns != streams.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&dem, &streams, verbose);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let dem = dem.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let grid_lengths = [
@@ -274,13 +224,13 @@ impl WhiteboxTool for ElevationAboveStream {
                     let mut data: Vec<i8> = vec![flow_nodata; columns as usize];
                     for col in 0..columns {
                         z = dem[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
                                 z_n = dem[(row + dy[i], col + dx[i])];
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -300,7 +250,9 @@ impl WhiteboxTool for ElevationAboveStream {
                             }
                         }
                     }
-                    tx.send((row, data, interior_pit_found)).unwrap();
+                    if tx.send((row, data, interior_pit_found)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -319,11 +271,11 @@ impl WhiteboxTool for ElevationAboveStream {
                 interior_pit_found = true;
             }
             for col in 0..columns {
-                if streams[(row, col)] > 0f64 && streams[(row, col)] != streams_nodata {
+                if streams[(row, col)] > 0f64 && !is_nodata(streams[(row, col)], streams_nodata) {
                     output[(row, col)] = 0f64;
                     stack.push((row, col, dem[(row, col)]));
                 }
-                if dem[(row, col)] == nodata {
+                if is_nodata(dem[(row, col)], nodata) {
                     output[(row, col)] = nodata;
                     num_solved_cells += 1;
                 }
@@ -335,10 +287,11 @@ impl WhiteboxTool for ElevationAboveStream {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow directions: {}%", progress);
+                    reporter.progress("Flow directions", progress);
                     old_progress = progress;
                 }
             }
@@ -360,18 +313,19 @@ impl WhiteboxTool for ElevationAboveStream {
                     && output[(row_n, col_n)] == background_value
                 {
                     stack.push((row_n, col_n, stream_elev));
-                    if stream_elev != nodata {
+                    if !is_nodata(stream_elev, nodata) {
                         output[(row_n, col_n)] = dem[(row_n, col_n)] - stream_elev;
                     } else {
                         output[(row_n, col_n)] = nodata;
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -11,7 +11,7 @@ use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to calculate the elevation of each grid cell in a raster above the nearest stream cell,
@@ -136,47 +136,12 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut dem_file = String::new();
-        let mut streams_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-dem" {
-                dem_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-streams" {
-                streams_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -189,20 +154,12 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !dem_file.contains(&sep) && !dem_file.contains("/") {
-            dem_file = format!("{}{}", working_directory, dem_file);
-        }
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -219,10 +176,7 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
         let mut distance: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
 
         if dem.configs.rows as isize != rows || dem.configs.columns as isize != columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Input DEM and streams file must have the same extent (rows and columns).",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("Input DEM and streams file must have the same extent (rows and columns)."));
         }
 
         let start = Instant::now();
@@ -242,7 +196,7 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != 0.0 && z != nodata {
+                if z != 0.0 && !is_nodata(z, nodata) {
                     distance.set_value(row, col, 0.0);
                     allocation.set_value(row, col, dem.get_value(row, col));
                 } else {
@@ -250,10 +204,11 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
                     allocation.set_value(row, col, inf_val);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Initializing Rasters: {}%", progress);
+                    reporter.progress("Initializing Rasters", progress);
                     old_progress = progress;
                 }
             }
@@ -269,7 +224,7 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = distance[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata(z2, nodata) {
                             h = match i {
                                 0 => 2.0 * r_x[(y, x)] + 1.0,
                                 1 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
@@ -293,10 +248,11 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (1 of 3): {}%", progress);
+                    reporter.progress("Progress (1 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -312,7 +268,7 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
                         x = col + d_x[i];
                         y = row + d_y[i];
                         z2 = distance[(y, x)];
-                        if z2 != nodata {
+                        if !is_nodata(z2, nodata) {
                             h = match i {
                                 5 => 2.0 * (r_x[(y, x)] + r_y[(y, x)] + 1.0),
                                 4 => 2.0 * r_x[(y, x)] + 1.0,
@@ -336,10 +292,11 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (2 of 3): {}%", progress);
+                    reporter.progress("Progress (2 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -348,7 +305,7 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
         for row in 0..rows {
             for col in 0..columns {
                 z = input[(row, col)];
-                if z == nodata {
+                if is_nodata(z, nodata) {
                     allocation.set_value(row, col, nodata);
                 } else {
                     allocation.set_value(
@@ -358,10 +315,11 @@ impl WhiteboxTool for ElevationAboveStreamEuclidean {
                     );
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (3 of 3): {}%", progress);
+                    reporter.progress("Progress (3 of 3)", progress);
                     old_progress = progress;
                 }
             }
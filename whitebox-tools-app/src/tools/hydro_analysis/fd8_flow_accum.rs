@@ -9,14 +9,12 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool is used to generate a flow accumulation grid (i.e. contributing area) using the FD8 algorithm (Freeman,
 /// 1991), sometimes referred to as FMFD. This algorithm is an examples of a multiple-flow-direction (MFD) method because the flow entering each
@@ -73,7 +71,7 @@ impl FD8FlowAccumulation {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -195,91 +193,31 @@ impl WhiteboxTool for FD8FlowAccumulation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut out_type = String::from("sca");
-        let mut exponent = 1.1;
+        let exponent = parsed.get_f64("exponent").unwrap_or(1.1);
         let mut convergence_threshold = f64::INFINITY;
-        let mut log_transform = false;
-        let mut clip_max = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        let log_transform = parsed.get_bool("log");
+        let clip_max = parsed.get_bool("clip");
+
+        if let Some(value) = parsed.get_string("out_type") {
+            out_type = value.to_lowercase();
+            out_type = if out_type.contains("specific") || out_type.contains("sca") {
+                String::from("sca")
+            } else if out_type.contains("cells") {
+                String::from("cells")
+            } else {
+                String::from("ca")
+            };
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_type" {
-                out_type = if keyval {
-                    vec[1].to_lowercase()
-                } else {
-                    args[i + 1].to_lowercase()
-                };
-                out_type = if out_type.contains("specific") || out_type.contains("sca") {
-                    String::from("sca")
-                } else if out_type.contains("cells") {
-                    String::from("cells")
-                } else {
-                    String::from("ca")
-                };
-            } else if flag_val == "-exponent" {
-                exponent = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-threshold" {
-                convergence_threshold = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if convergence_threshold == 0f64 {
-                    convergence_threshold = f64::INFINITY;
-                }
-            } else if flag_val == "-log" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    log_transform = true;
-                }
-            } else if flag_val == "-clip" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    clip_max = true;
-                }
+        if let Some(value) = parsed.get_f64("threshold") {
+            convergence_threshold = value;
+            if convergence_threshold == 0f64 {
+                convergence_threshold = f64::INFINITY;
             }
         }
 
@@ -294,17 +232,11 @@ impl WhiteboxTool for FD8FlowAccumulation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -328,19 +260,16 @@ impl WhiteboxTool for FD8FlowAccumulation {
         let mut num_solved_cells = 0;
         let mut interior_pit_found = false;
         let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let pool = whitebox_common::utils::ThreadPool::new(&configs);
+        let num_procs = pool.num_threads() as isize;
 
         // calculate the number of inflowing cells
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            pool.spawn(tid as usize, move || {
                 let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
                 let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let mut z: f64;
@@ -351,11 +280,11 @@ impl WhiteboxTool for FD8FlowAccumulation {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 zn = input.get_value(row + d_y[i], col + d_x[i]);
-                                if zn > z && zn != nodata {
+                                if zn > z && !is_nodata(zn, nodata) {
                                     count += 1;
                                 }
                             }
@@ -365,8 +294,9 @@ impl WhiteboxTool for FD8FlowAccumulation {
                             }
                         }
                     }
-                    tx.send((row, data, interior_pit_found))
-                        .expect("Error sending data to thread.");
+                    if tx.send((row, data, interior_pit_found)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -385,10 +315,11 @@ impl WhiteboxTool for FD8FlowAccumulation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -429,7 +360,7 @@ impl WhiteboxTool for FD8FlowAccumulation {
                     row_n = row + d_y[i];
                     col_n = col + d_x[i];
                     z_n = input[(row_n, col_n)];
-                    if z_n < z && z_n != nodata {
+                    if z_n < z && !is_nodata(z_n, nodata) {
                         slope = (z - z_n) / grid_lengths[i];
                         weights[i] = slope.powf(exponent);
                         total_weights += weights[i];
@@ -442,7 +373,7 @@ impl WhiteboxTool for FD8FlowAccumulation {
                 max_slope = f64::MIN;
                 for i in 0..8 {
                     z_n = input[(row + d_y[i], col + d_x[i])];
-                    if z_n != nodata {
+                    if !is_nodata(z_n, nodata) {
                         slope = (z - z_n) / grid_lengths[i];
                         if slope > 0f64 {
                             downslope[i] = true;
@@ -473,11 +404,12 @@ impl WhiteboxTool for FD8FlowAccumulation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow accumulation: {}%", progress);
+                    reporter.progress("Flow accumulation", progress);
                     old_progress = progress;
                 }
             }
@@ -495,17 +427,18 @@ impl WhiteboxTool for FD8FlowAccumulation {
         if log_transform {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
+                    if is_nodata(input[(row, col)], nodata) {
                         output[(row, col)] = nodata;
                     } else {
                         output[(row, col)] = (output[(row, col)] * cell_area / avg_cell_size).ln();
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
@@ -513,17 +446,18 @@ impl WhiteboxTool for FD8FlowAccumulation {
         } else {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
+                    if is_nodata(input[(row, col)], nodata) {
                         output[(row, col)] = nodata;
                     } else {
                         output[(row, col)] = output[(row, col)] * cell_area / avg_cell_size;
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
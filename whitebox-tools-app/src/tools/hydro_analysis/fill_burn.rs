@@ -10,7 +10,6 @@ use whitebox_raster::*;
 use whitebox_common::structures::{Array2D, BoundingBox};
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
-use num_cpus;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::collections::VecDeque;
@@ -20,7 +19,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// Burns streams into a DEM using the FillBurn (Saunders, 1999) method which produces a hydro-enforced DEM.
 /// This tool uses the algorithm described in:
@@ -143,47 +142,12 @@ impl WhiteboxTool for FillBurn {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut dem_file = String::new();
-        let mut streams_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-dem" {
-                dem_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-streams" {
-                streams_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -196,20 +160,12 @@ impl WhiteboxTool for FillBurn {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !dem_file.contains(&sep) && !dem_file.contains("/") {
-            dem_file = format!("{}{}", working_directory, dem_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading streams data...")
@@ -347,10 +303,11 @@ impl WhiteboxTool for FillBurn {
             }
 
             count += 1f64;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * count / (streams.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Rasterizing Streams: {}%", progress);
+                    reporter.progress("Rasterizing Streams", progress);
                     old_progress = progress;
                 }
             }
@@ -413,10 +370,11 @@ impl WhiteboxTool for FillBurn {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * a as f64 / 7.0) as usize;
                     if progress != old_progress {
-                        println!("Line Thinning {}: {}%", loop_num, progress);
+                        reporter.progress(&format!("Line Thinning {}", loop_num), progress);
                         old_progress = progress;
                     }
                 }
@@ -426,30 +384,28 @@ impl WhiteboxTool for FillBurn {
         // Make a copy of the DEM where each stream cell
         //  has been lowered by 10,000 elevation units.
         let raster_streams = Arc::new(raster_streams);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let dem = dem.clone();
             let raster_streams = raster_streams.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = dem.get_value(row, col);
-                        if raster_streams.get_value(row, col) == 0u8 && z != nodata {
+                        if raster_streams.get_value(row, col) == 0u8 && !is_nodata(z, nodata) {
                             data[col as usize] = z;
-                        } else if raster_streams.get_value(row, col) == 1u8 && z != nodata {
+                        } else if raster_streams.get_value(row, col) == 1u8 && !is_nodata(z, nodata) {
                             data[col as usize] = z - 10000f64;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -459,10 +415,11 @@ impl WhiteboxTool for FillBurn {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Initializing output: {}%", progress);
+                    reporter.progress("Initializing output", progress);
                     old_progress = progress;
                 }
             }
@@ -484,6 +441,7 @@ impl WhiteboxTool for FillBurn {
         let mut queue: VecDeque<(isize, isize)> =
             VecDeque::with_capacity((rows * columns) as usize);
         for row in 0..rows {
+            reporter.check_cancelled()?;
             /*
             Note that this is only possible because Whitebox rasters
             allow you to address cells beyond the raster extent but
@@ -520,7 +478,7 @@ impl WhiteboxTool for FillBurn {
                 row_n = row + dy[n];
                 col_n = col + dx[n];
                 if in_queue.get_value(row_n, col_n) == 0u8 {
-                    if dem.get_value(row_n, col_n) == nodata {
+                    if is_nodata(dem.get_value(row_n, col_n), nodata) {
                         queue.push_back((row_n, col_n));
                     } else {
                         // Push it onto the priority queue for the priority flood operation
@@ -535,10 +493,11 @@ impl WhiteboxTool for FillBurn {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -561,7 +520,7 @@ impl WhiteboxTool for FillBurn {
                 if in_queue.get_value(row_n, col_n) == 0u8 {
                     zout_n = output.get_value(row_n, col_n);
                     // zin_n = input[(row_n, col_n)];
-                    if zout_n != nodata {
+                    if !is_nodata(zout_n, nodata) {
                         if zout_n < (zout + small_num) {
                             zout_n = zout + small_num;
                         } // We're in a depression. Raise the elevation.
@@ -576,11 +535,12 @@ impl WhiteboxTool for FillBurn {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Filling DEM: {}%", progress);
+                    reporter.progress("Filling DEM", progress);
                     old_progress = progress;
                 }
             }
@@ -593,17 +553,18 @@ impl WhiteboxTool for FillBurn {
         let mut min_diff = f64::INFINITY;
         for row in 0..rows {
             for col in 0..columns {
-                if raster_streams.get_value(row, col) > 0u8 && dem.get_value(row, col) != nodata {
+                if raster_streams.get_value(row, col) > 0u8 && !is_nodata(dem.get_value(row, col), nodata) {
                     z = dem.get_value(row, col) - output.get_value(row, col);
                     if z < min_diff {
                         min_diff = z;
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Updating Stream Elevations: {}%", progress);
+                    reporter.progress("Updating Stream Elevations", progress);
                     old_progress = progress;
                 }
             }
@@ -613,15 +574,16 @@ impl WhiteboxTool for FillBurn {
 
         for row in 0..rows {
             for col in 0..columns {
-                if raster_streams.get_value(row, col) > 0u8 && dem.get_value(row, col) != nodata {
+                if raster_streams.get_value(row, col) > 0u8 && !is_nodata(dem.get_value(row, col), nodata) {
                     z = output.get_value(row, col) + min_diff;
                     output.set_value(row, col, z);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Updating Stream Elevations: {}%", progress);
+                    reporter.progress("Updating Stream Elevations", progress);
                     old_progress = progress;
                 }
             }
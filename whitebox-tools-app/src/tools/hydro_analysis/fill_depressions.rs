@@ -15,11 +15,10 @@ use std::collections::{BinaryHeap, VecDeque};
 use std::env;
 use std::f64;
 use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool can be used to fill all of the depressions in a digital elevation model (DEM) and to remove the
 /// flat areas. This is a common pre-processing step required by many flow-path analysis tools to ensure continuous
@@ -74,7 +73,7 @@ impl FillDepressions {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -97,7 +96,7 @@ impl FillDepressions {
                 "Optional flag indicating whether flat areas should have a small gradient applied."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -181,71 +180,14 @@ impl WhiteboxTool for FillDepressions {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut fix_flats = false;
-        let mut flat_increment = f64::NAN;
-        let mut max_depth = f64::INFINITY;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-fix_flats" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    fix_flats = true;
-                }
-            } else if flag_val == "-flat_increment" {
-                flat_increment = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_depth" {
-                max_depth = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let fix_flats = parsed.get_bool("fix_flats");
+        let flat_increment = parsed.get_f64("flat_increment").unwrap_or(f64::NAN);
+        let max_depth = parsed.get_f64("max_depth").unwrap_or(f64::INFINITY);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -258,17 +200,11 @@ impl WhiteboxTool for FillDepressions {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -309,18 +245,15 @@ impl WhiteboxTool for FillDepressions {
         let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
 
         // Find pit cells. This step is parallelized.
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let pool = whitebox_common::utils::ThreadPool::new(&configs);
+        let num_procs = pool.num_threads() as isize;
         let output2 = Arc::new(output);
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let output2 = output2.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            pool.spawn(tid as usize, move || {
                 let mut z: f64;
                 let mut zn: f64;
                 let mut flag: bool;
@@ -328,11 +261,11 @@ impl WhiteboxTool for FillDepressions {
                 for row in (1..rows - 1).filter(|r| r % num_procs == tid) {
                     for col in 1..columns - 1 {
                         z = output2.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             flag = true;
                             for n in 0..8 {
                                 zn = output2.get_value(row + dy[n], col + dx[n]);
-                                if zn < z || zn == nodata {
+                                if zn < z || is_nodata(zn, nodata) {
                                     // It either has a lower neighbour or is an edge cell.
                                     flag = false;
                                     break;
@@ -345,7 +278,9 @@ impl WhiteboxTool for FillDepressions {
                         }
                     }
                 }
-                tx.send(pits).unwrap();
+                if tx.send(pits).is_err() {
+                    return;
+                }
             });
         }
 
@@ -354,10 +289,11 @@ impl WhiteboxTool for FillDepressions {
             let mut pits = rx.recv().expect("Error receiving data from thread.");
             undefined_flow_cells.append(&mut pits);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (p + 1) as f64 / num_procs as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding pit cells: {}%", progress);
+                    reporter.progress("Finding pit cells", progress);
                     old_progress = progress;
                 }
             }
@@ -421,14 +357,14 @@ impl WhiteboxTool for FillDepressions {
                             if visited.get_value(rn, cn) == 0 {
                                 zn = output.get_value(rn, cn);
                                 if !outlet_found {
-                                    if zn >= z && zn != nodata {
+                                    if zn >= z && !is_nodata(zn, nodata) {
                                         minheap.push(GridCell {
                                             row: rn,
                                             column: cn,
                                             priority: zn,
                                         });
                                         visited.set_value(rn, cn, 1);
-                                    } else if zn != nodata {
+                                    } else if !is_nodata(zn, nodata) {
                                         // zn < z
                                         // 'cell' has a lower neighbour that hasn't already passed through minheap.
                                         // Therefore, 'cell' is a pour point cell.
@@ -515,10 +451,11 @@ impl WhiteboxTool for FillDepressions {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * pit_id as f64 / num_deps as f64) as usize;
                 if progress != old_progress {
-                    println!("Filling depressions: {}%", progress);
+                    reporter.progress("Filling depressions", progress);
                     old_progress = progress;
                 }
             }
@@ -537,13 +474,14 @@ impl WhiteboxTool for FillDepressions {
             // let mut queue = VecDeque::new();
             minheap.clear();
             while let Some(cell) = possible_outlets.pop() {
+                reporter.check_cancelled()?;
                 z = output.get_value(cell.0, cell.1);
                 flag = false;
                 for n in 0..8 {
                     rn = cell.0 + dy[n];
                     cn = cell.1 + dx[n];
                     zn = output.get_value(rn, cn);
-                    if zn < z && zn != nodata {
+                    if zn < z && !is_nodata(zn, nodata) {
                         flag = true;
                         break;
                     }
@@ -597,7 +535,7 @@ impl WhiteboxTool for FillDepressions {
                             cn = cell2.column + dx[n];
                             if flats.get_value(rn, cn) != 3 {
                                 zn = output.get_value(rn, cn);
-                                if zn == z && zn != nodata {
+                                if zn == z && !is_nodata(zn, nodata) {
                                     // queue.push_back((rn, cn, z));
                                     minheap2.push(GridCell2 {
                                         row: rn,
@@ -619,7 +557,7 @@ impl WhiteboxTool for FillDepressions {
                             cn = cell2.column + dx[n];
                             if flats.get_value(rn, cn) != 3 {
                                 zn = output.get_value(rn, cn);
-                                if zn < z + small_num && zn >= cell2.z && zn != nodata {
+                                if zn < z + small_num && zn >= cell2.z && !is_nodata(zn, nodata) {
                                     // queue.push_back((rn, cn, cell2.2));
                                     minheap2.push(GridCell2 {
                                         row: rn,
@@ -650,11 +588,12 @@ impl WhiteboxTool for FillDepressions {
                     // }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * (1f64 - minheap.len() as f64 / num_outlets as f64)) as usize;
                     if progress != old_progress {
-                        println!("Fixing flats: {}%", progress);
+                        reporter.progress("Fixing flats", progress);
                         old_progress = progress;
                     }
                 }
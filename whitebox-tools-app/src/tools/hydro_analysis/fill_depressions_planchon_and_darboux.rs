@@ -14,7 +14,7 @@ use crate::tools::*;
 use std::env;
 use std::f64;
 use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to fill all of the depressions in a digital elevation model (DEM) and to remove the
@@ -53,7 +53,7 @@ impl FillDepressionsPlanchonAndDarboux {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -76,7 +76,7 @@ impl FillDepressionsPlanchonAndDarboux {
                 "Optional flag indicating whether flat areas should have a small gradient applied."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -151,58 +151,13 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut fix_flats = false;
-        let mut flat_increment = f64::NAN;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-fix_flats" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    fix_flats = true;
-                }
-            } else if flag_val == "-flat_increment" {
-                flat_increment = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let fix_flats = parsed.get_bool("fix_flats");
+        let flat_increment = parsed.get_f64("flat_increment").unwrap_or(f64::NAN);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -215,17 +170,11 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -277,9 +226,10 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
         let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
         let mut stack = vec![];
         for row in 0..rows {
+            reporter.check_cancelled()?;
             z = input.get_value(row, 0);
             w = output.get_value(row, 0);
-            if z != nodata {
+            if !is_nodata(z, nodata) {
                 output.set_value(row, 0, z);
             } else if w == large_value {
                 output.set_value(row, 0, nodata_output);
@@ -291,7 +241,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                         w = output.get_value(rn, cn);
                         if w == large_value {
                             z = input.get_value(rn, cn);
-                            if z == nodata {
+                            if is_nodata(z, nodata) {
                                 output.set_value(rn, cn, nodata_output);
                                 stack.push((rn, cn));
                             } else {
@@ -304,7 +254,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
 
             z = input.get_value(row, columns - 1);
             w = output.get_value(row, columns - 1);
-            if z != nodata {
+            if !is_nodata(z, nodata) {
                 output.set_value(row, columns - 1, z);
             } else if w == large_value {
                 output.set_value(row, columns - 1, nodata_output);
@@ -316,7 +266,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                         w = output.get_value(rn, cn);
                         if w == large_value {
                             z = input.get_value(rn, cn);
-                            if z == nodata {
+                            if is_nodata(z, nodata) {
                                 output.set_value(rn, cn, nodata_output);
                                 stack.push((rn, cn));
                             } else {
@@ -331,7 +281,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
         for col in 0..columns {
             z = input.get_value(0, col);
             w = output.get_value(0, col);
-            if z != nodata {
+            if !is_nodata(z, nodata) {
                 output.set_value(0, col, z);
             } else if w == large_value {
                 output.set_value(0, col, nodata_output);
@@ -343,7 +293,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                         w = output.get_value(rn, cn);
                         if w == large_value {
                             z = input.get_value(rn, cn);
-                            if z == nodata {
+                            if is_nodata(z, nodata) {
                                 output.set_value(rn, cn, nodata_output);
                                 stack.push((rn, cn));
                             } else {
@@ -356,7 +306,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
 
             z = input.get_value(rows - 1, col);
             w = output.get_value(rows - 1, col);
-            if z != nodata {
+            if !is_nodata(z, nodata) {
                 output.set_value(rows - 1, col, z);
             } else if w == large_value {
                 output.set_value(rows - 1, col, nodata_output);
@@ -368,7 +318,7 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                         w = output.get_value(rn, cn);
                         if w == large_value {
                             z = input.get_value(rn, cn);
-                            if z == nodata {
+                            if is_nodata(z, nodata) {
                                 output.set_value(rn, cn, nodata_output);
                                 stack.push((rn, cn));
                             } else {
@@ -392,13 +342,13 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                     for col in 1..columns - 1 {
                         z = input.get_value(row, col);
                         w = output.get_value(row, col);
-                        if w != nodata_output {
+                        if !is_nodata(w, nodata_output) {
                             if w > z {
                                 for n in 0..8 {
                                     rn = row + dy[n];
                                     cn = col + dx[n];
                                     wn = output.get_value(rn, cn);
-                                    if wn != nodata_output {
+                                    if !is_nodata(wn, nodata_output) {
                                         wn += small_num;
                                         if z >= wn {
                                             // operation 1
@@ -418,10 +368,11 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("progress (Loop {}): {}%", loop_num, progress);
+                            reporter.progress(&format!("progress (Loop {})", loop_num), progress);
                             old_progress = progress;
                         }
                     }
@@ -431,13 +382,13 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                     for col in (1..columns - 1).rev() {
                         z = input.get_value(row, col);
                         w = output.get_value(row, col);
-                        if w != nodata_output {
+                        if !is_nodata(w, nodata_output) {
                             if w > z {
                                 for n in 0..8 {
                                     rn = row + dy[n];
                                     cn = col + dx[n];
                                     wn = output.get_value(rn, cn);
-                                    if wn != nodata_output {
+                                    if !is_nodata(wn, nodata_output) {
                                         wn += small_num;
                                         if z >= wn {
                                             // operation 1
@@ -457,10 +408,11 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (rows - row) as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("progress (Loop {}): {}%", loop_num, progress);
+                            reporter.progress(&format!("progress (Loop {})", loop_num), progress);
                             old_progress = progress;
                         }
                     }
@@ -470,13 +422,13 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                     for col in (1..columns - 1).rev() {
                         z = input.get_value(row, col);
                         w = output.get_value(row, col);
-                        if w != nodata_output {
+                        if !is_nodata(w, nodata_output) {
                             if w > z {
                                 for n in 0..8 {
                                     rn = row + dy[n];
                                     cn = col + dx[n];
                                     wn = output.get_value(rn, cn);
-                                    if wn != nodata_output {
+                                    if !is_nodata(wn, nodata_output) {
                                         wn += small_num;
                                         if z >= wn {
                                             // operation 1
@@ -496,10 +448,11 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("progress (Loop {}): {}%", loop_num, progress);
+                            reporter.progress(&format!("progress (Loop {})", loop_num), progress);
                             old_progress = progress;
                         }
                     }
@@ -510,13 +463,13 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                     for col in 1..columns - 1 {
                         z = input.get_value(row, col);
                         w = output.get_value(row, col);
-                        if w != nodata_output {
+                        if !is_nodata(w, nodata_output) {
                             if w > z {
                                 for n in 0..8 {
                                     rn = row + dy[n];
                                     cn = col + dx[n];
                                     wn = output.get_value(rn, cn);
-                                    if wn != nodata_output {
+                                    if !is_nodata(wn, nodata_output) {
                                         wn += small_num;
                                         if z >= wn {
                                             // operation 1
@@ -536,10 +489,11 @@ impl WhiteboxTool for FillDepressionsPlanchonAndDarboux {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * (rows - row) as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("progress (Loop {}): {}%", loop_num, progress);
+                            reporter.progress(&format!("progress (Loop {})", loop_num), progress);
                             old_progress = progress;
                         }
                     }
@@ -19,7 +19,7 @@ use std::collections::VecDeque;
 use std::env;
 use std::f64;
 use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to fill all of the depressions in a digital elevation model (DEM) and to remove the
@@ -65,7 +65,7 @@ impl FillDepressionsWangAndLiu {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -88,7 +88,7 @@ impl FillDepressionsWangAndLiu {
                 "Optional flag indicating whether flat areas should have a small gradient applied."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -163,58 +163,13 @@ impl WhiteboxTool for FillDepressionsWangAndLiu {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut fix_flats = false;
-        let mut flat_increment = f64::NAN;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-fix_flats" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    fix_flats = true;
-                }
-            } else if flag_val == "-flat_increment" {
-                flat_increment = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let fix_flats = parsed.get_bool("fix_flats");
+        let flat_increment = parsed.get_f64("flat_increment").unwrap_or(f64::NAN);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -227,17 +182,11 @@ impl WhiteboxTool for FillDepressionsWangAndLiu {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -290,6 +239,7 @@ impl WhiteboxTool for FillDepressionsWangAndLiu {
         let mut queue: VecDeque<(isize, isize)> =
             VecDeque::with_capacity((rows * columns) as usize);
         for row in 0..rows {
+            reporter.check_cancelled()?;
             /*
             Note that this is only possible because Whitebox rasters
             allow you to address cells beyond the raster extent but
@@ -328,7 +278,7 @@ impl WhiteboxTool for FillDepressionsWangAndLiu {
                 zin_n = input.get_value(row_n, col_n);
                 zout_n = output[(row_n, col_n)];
                 if zout_n == background_val {
-                    if zin_n == nodata {
+                    if is_nodata(zin_n, nodata) {
                         output.set_value(row_n, col_n, nodata);
                         queue.push_back((row_n, col_n));
                     } else {
@@ -344,10 +294,11 @@ impl WhiteboxTool for FillDepressionsWangAndLiu {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("progress: {}%", progress);
+                    reporter.progress("progress", progress);
                     old_progress = progress;
                 }
             }
@@ -370,7 +321,7 @@ impl WhiteboxTool for FillDepressionsWangAndLiu {
                 zout_n = output[(row_n, col_n)];
                 if zout_n == background_val {
                     zin_n = input[(row_n, col_n)];
-                    if zin_n != nodata {
+                    if !is_nodata(zin_n, nodata) {
                         if zin_n < (zout + small_num) {
                             zin_n = zout + small_num;
                         } // We're in a depression. Raise the elevation.
@@ -388,11 +339,12 @@ impl WhiteboxTool for FillDepressionsWangAndLiu {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
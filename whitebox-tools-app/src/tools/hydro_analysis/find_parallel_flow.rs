@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to find cells in a stream network grid that possess parallel flow directions based
 /// on an input D8 flow-pointer grid (`D8Pointer`). Because streams rarely flow in parallel for significant
@@ -56,7 +55,7 @@ impl FindParallelFlow {
             description: "Input raster streams file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
-            optional: false,
+            optional: true,
         });
 
         parameters.push(ToolParameter {
@@ -131,48 +130,18 @@ impl WhiteboxTool for FindParallelFlow {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut d8_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("d8_pntr").unwrap_or_default();
         let mut use_streams = false;
         let mut streams_file = String::new();
-        let mut output_file = String::new();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-d8_pntr" || vec[0].to_lowercase() == "--d8_pntr" {
-                if keyval {
-                    d8_file = vec[1].to_string();
-                } else {
-                    d8_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-streams" || vec[0].to_lowercase() == "--streams" {
-                if keyval {
-                    streams_file = vec[1].to_string();
-                } else {
-                    streams_file = args[i + 1].to_string();
-                }
-                if !streams_file.is_empty() {
-                    use_streams = true;
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
+        if let Some(value) = parsed.get_string("streams") {
+            streams_file = value.to_string();
+            if !streams_file.is_empty() {
+                use_streams = true;
             }
         }
 
@@ -187,14 +156,8 @@ impl WhiteboxTool for FindParallelFlow {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !d8_file.contains(&sep) && !d8_file.contains("/") {
-            d8_file = format!("{}{}", working_directory, d8_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        d8_file = resolve_input_path(&d8_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let pntr = Arc::new(Raster::new(&d8_file, "r")?);
 
@@ -218,8 +181,7 @@ impl WhiteboxTool for FindParallelFlow {
                 };
                 let r = Raster::new(&streams_file, "r")?;
                 if r.configs.rows != rows as usize || r.configs.columns != columns as usize {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                        "The input files must have the same number of rows and columns and spatial extent."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
                 }
                 streams_nodata = r.configs.nodata;
                 r.get_data_as_array2d()
@@ -229,18 +191,14 @@ impl WhiteboxTool for FindParallelFlow {
         let mut output = Raster::initialize_using_file(&output_file, &pntr);
         let streams = Arc::new(streams);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let pntr = pntr.clone();
             let streams = streams.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut zn: f64;
                 let mut stream_val: f64;
@@ -255,7 +213,7 @@ impl WhiteboxTool for FindParallelFlow {
                     for col in 0..columns {
                         z = pntr[(row, col)];
                         stream_val = streams[(row, col)];
-                        if z != nodata && stream_val != streams_nodata && stream_val > 0f64 {
+                        if !is_nodata(z, nodata) && !is_nodata(stream_val, streams_nodata) && stream_val > 0f64 {
                             is_parallel = false;
                             for n in 0..8 {
                                 if z != outflowing_vals[n] {
@@ -264,7 +222,7 @@ impl WhiteboxTool for FindParallelFlow {
                                     if zn == z
                                         && zn != inflowing_vals[n]
                                         && stream_valn > 0f64
-                                        && stream_valn != streams_nodata
+                                        && !is_nodata(stream_valn, streams_nodata)
                                     {
                                         is_parallel = true;
                                         break;
@@ -278,7 +236,9 @@ impl WhiteboxTool for FindParallelFlow {
                             }
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -286,10 +246,11 @@ impl WhiteboxTool for FindParallelFlow {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
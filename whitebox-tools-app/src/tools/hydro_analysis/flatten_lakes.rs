@@ -136,47 +136,12 @@ impl WhiteboxTool for FlattenLakes {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut polygons_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-lakes" {
-                polygons_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut polygons_file = parsed.get_string("lakes").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -189,20 +154,12 @@ impl WhiteboxTool for FlattenLakes {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !polygons_file.contains(&sep) && !polygons_file.contains("/") {
-            polygons_file = format!("{}{}", working_directory, polygons_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        polygons_file = resolve_input_path(&polygons_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -233,6 +190,7 @@ impl WhiteboxTool for FlattenLakes {
         // how many hulls are there?
         let mut num_hulls = 0;
         for record_num in 0..polygons.num_records {
+            reporter.check_cancelled()?;
             let record = polygons.get_record(record_num);
             for part in 0..record.num_parts {
                 if !record.is_hole(part) {
@@ -331,7 +289,7 @@ impl WhiteboxTool for FlattenLakes {
                                         let col = input.get_column_from_x(x_prime);
 
                                         z = input.get_value(row, col);
-                                        if z != nodata {
+                                        if !is_nodata(z, nodata) {
                                             if z < min_elevs[feature_num] {
                                                 min_elevs[feature_num] = z;
                                             }
@@ -362,7 +320,7 @@ impl WhiteboxTool for FlattenLakes {
                                         let row = output.get_row_from_y(y_prime);
 
                                         z = input.get_value(row, col);
-                                        if z != nodata {
+                                        if !is_nodata(z, nodata) {
                                             if z < min_elevs[feature_num] {
                                                 min_elevs[feature_num] = z;
                                             }
@@ -378,10 +336,11 @@ impl WhiteboxTool for FlattenLakes {
             }
 
             count += 1f64;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * count / (polygons.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding lake elevations: {}%", progress);
+                    reporter.progress("Finding lake elevations", progress);
                     old_progress = progress;
                 }
             }
@@ -456,17 +415,13 @@ impl WhiteboxTool for FlattenLakes {
                                     output.set_value(r, c, min_elevs[feature_num]);
                                 }
                             }
+                            reporter.check_cancelled()?;
                             if verbose && num_records < 25 {
                                 progress = (100.0_f64 * (r - starting_row) as f64
                                     / (ending_row - starting_row) as f64)
                                     as usize;
                                 if progress != old_progress {
-                                    println!(
-                                        "Updating lake elevations ({} of {}): {}%",
-                                        record_num + 1,
-                                        num_records,
-                                        progress
-                                    );
+                                    reporter.progress(&format!("Updating lake elevations ({} of {})", record_num + 1, num_records), progress);
                                     old_progress = progress;
                                 }
                             }
@@ -532,17 +487,13 @@ impl WhiteboxTool for FlattenLakes {
                                 output.set_value(r, c, input.get_value(r, c));
                             }
                         }
+                        reporter.check_cancelled()?;
                         if verbose && num_records < 25 {
                             progress = (100.0_f64 * (r - starting_row) as f64
                                 / (ending_row - starting_row) as f64)
                                 as usize;
                             if progress != old_progress {
-                                println!(
-                                    "Updating lake elevations ({} of {}): {}%",
-                                    record_num + 1,
-                                    num_records,
-                                    progress
-                                );
+                                reporter.progress(&format!("Updating lake elevations ({} of {})", record_num + 1, num_records), progress);
                                 old_progress = progress;
                             }
                         }
@@ -550,10 +501,11 @@ impl WhiteboxTool for FlattenLakes {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose && num_records > 25 {
                 progress = (100.0_f64 * (record_num as f64) / (num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -15,7 +15,7 @@ use std::collections::VecDeque;
 use std::env;
 use std::f64;
 use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool takes an input digital elevation model (DEM) and creates an output raster where every grid cell
@@ -54,7 +54,7 @@ impl FloodOrder {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -132,42 +132,11 @@ impl WhiteboxTool for FloodOrder {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i"
-                || vec[0].to_lowercase() == "--input"
-                || vec[0].to_lowercase() == "--dem"
-            {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -180,17 +149,11 @@ impl WhiteboxTool for FloodOrder {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -226,6 +189,7 @@ impl WhiteboxTool for FloodOrder {
         let mut queue: VecDeque<(isize, isize)> =
             VecDeque::with_capacity((rows * columns) as usize);
         for row in 0..rows {
+            reporter.check_cancelled()?;
             /*
             Note that this is only possible because Whitebox rasters
             allow you to address cells beyond the raster extent but
@@ -264,7 +228,7 @@ impl WhiteboxTool for FloodOrder {
                 zin_n = input[(row_n, col_n)];
                 zout_n = filled_dem[(row_n, col_n)];
                 if zout_n == background_val {
-                    if zin_n == nodata {
+                    if is_nodata(zin_n, nodata) {
                         filled_dem[(row_n, col_n)] = nodata;
                         output[(row_n, col_n)] = nodata;
                         queue.push_back((row_n, col_n));
@@ -281,10 +245,11 @@ impl WhiteboxTool for FloodOrder {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("progress: {}%", progress);
+                    reporter.progress("progress", progress);
                     old_progress = progress;
                 }
             }
@@ -305,7 +270,7 @@ impl WhiteboxTool for FloodOrder {
                 zout_n = filled_dem[(row_n, col_n)];
                 if zout_n == background_val {
                     zin_n = input[(row_n, col_n)];
-                    if zin_n != nodata {
+                    if !is_nodata(zin_n, nodata) {
                         if zin_n < zout {
                             zin_n = zout;
                         } // We're in a depression. Raise the elevation.
@@ -323,11 +288,12 @@ impl WhiteboxTool for FloodOrder {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -12,18 +12,16 @@ the D8 pointer raster and finally the D8 flow accumulation operation.
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::collections::VecDeque;
 use std::env;
 use std::f64;
 use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// Resolves all of the depressions in a DEM, outputting a breached DEM, an aspect-aligned non-divergent flow
 /// pointer, and a flow accumulation raster.
@@ -45,7 +43,7 @@ impl FlowAccumulationFullWorkflow {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -112,7 +110,7 @@ impl FlowAccumulationFullWorkflow {
 
         parameters.push(ToolParameter {
             name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -177,90 +175,26 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut outdem_file = String::new();
-        let mut pntr_file = String::new();
-        let mut accum_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut outdem_file = parsed.get_string("out_dem").unwrap_or_default();
+        let mut pntr_file = parsed.get_string("out_pntr").unwrap_or_default();
+        let mut accum_file = parsed.get_string("out_accum").unwrap_or_default();
         let mut out_type = String::from("sca");
-        let mut log_transform = false;
-        let mut clip_max = false;
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i"
-                || vec[0].to_lowercase() == "--input"
-                || vec[0].to_lowercase() == "--dem"
-            {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-out_dem" || vec[0].to_lowercase() == "--out_dem" {
-                if keyval {
-                    outdem_file = vec[1].to_string();
-                } else {
-                    outdem_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-out_pntr" || vec[0].to_lowercase() == "--out_pntr"
-            {
-                if keyval {
-                    pntr_file = vec[1].to_string();
-                } else {
-                    pntr_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-out_accum"
-                || vec[0].to_lowercase() == "--out_accum"
-            {
-                if keyval {
-                    accum_file = vec[1].to_string();
-                } else {
-                    accum_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-out_type" || vec[0].to_lowercase() == "--out_type"
-            {
-                if keyval {
-                    out_type = vec[1].to_lowercase();
-                } else {
-                    out_type = args[i + 1].to_lowercase();
-                }
-                if out_type.contains("specific") || out_type.contains("sca") {
-                    out_type = String::from("sca");
-                } else if out_type.contains("cells") {
-                    out_type = String::from("cells");
-                } else {
-                    out_type = String::from("ca");
-                }
-            } else if vec[0].to_lowercase() == "-log" || vec[0].to_lowercase() == "--log" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    log_transform = true;
-                }
-            } else if vec[0].to_lowercase() == "-clip" || vec[0].to_lowercase() == "--clip" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    clip_max = true;
-                }
-            } else if vec[0].to_lowercase() == "-esri_style"
-                || vec[0].to_lowercase() == "--esri_style"
-                || vec[0].to_lowercase() == "--esri_pntr"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
+        let log_transform = parsed.get_bool("log");
+        let clip_max = parsed.get_bool("clip");
+        let esri_style = parsed.get_bool("esri_pntr");
+
+        if let Some(value) = parsed.get_string("out_type") {
+            out_type = value.to_lowercase();
+            if out_type.contains("specific") || out_type.contains("sca") {
+                out_type = String::from("sca");
+            } else if out_type.contains("cells") {
+                out_type = String::from("cells");
+            } else {
+                out_type = String::from("ca");
             }
         }
 
@@ -275,23 +209,13 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !outdem_file.contains(&sep) && !outdem_file.contains("/") {
-            outdem_file = format!("{}{}", working_directory, outdem_file);
-        }
-        if !pntr_file.contains(&sep) && !pntr_file.contains("/") {
-            pntr_file = format!("{}{}", working_directory, pntr_file);
-        }
-        if !accum_file.contains(&sep) && !accum_file.contains("/") {
-            accum_file = format!("{}{}", working_directory, accum_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        outdem_file = resolve_path(&outdem_file, working_directory);
+        pntr_file = resolve_path(&pntr_file, working_directory);
+        accum_file = resolve_path(&accum_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -321,17 +245,14 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
 
         let eight_grid_res = input.configs.resolution_x * 8.0;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let pool = whitebox_common::utils::ThreadPool::new(&configs);
+        let num_procs = pool.num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            pool.spawn(tid as usize, move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let mut n: [f64; 8] = [0.0; 8];
@@ -341,10 +262,10 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             for c in 0..8 {
                                 n[c] = input[(row + dy[c], col + dx[c])];
-                                if n[c] != nodata {
+                                if !is_nodata(n[c], nodata) {
                                     n[c] = n[c] * z_factor;
                                 } else {
                                     n[c] = z * z_factor;
@@ -361,7 +282,9 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                             }
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -371,10 +294,11 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
             let data = rx.recv().expect("Error receiving data from thread.");
             aspect.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating aspect: {}%", progress);
+                    reporter.progress("Calculating aspect", progress);
                     old_progress = progress;
                 }
             }
@@ -403,6 +327,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
         let mut queue: VecDeque<(isize, isize)> =
             VecDeque::with_capacity((rows * columns) as usize);
         for row in 0..rows {
+            reporter.check_cancelled()?;
             /*
             Note that this is only possible because Whitebox rasters
             allow you to address cells beyond the raster extent but
@@ -443,7 +368,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                 zin_n = input[(row_n, col_n)];
                 zout_n = output[(row_n, col_n)];
                 if zout_n == background_val {
-                    if zin_n == nodata {
+                    if is_nodata(zin_n, nodata) {
                         output[(row_n, col_n)] = nodata;
                         queue.push_back((row_n, col_n));
                     } else {
@@ -452,7 +377,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                         for p in 0..8 {
                             y = row_n + dy[p];
                             x = col_n + dx[p];
-                            if input[(y, x)] < zin_n && input[(y, x)] != nodata {
+                            if input[(y, x)] < zin_n && !is_nodata(input[(y, x)], nodata) {
                                 is_lowest = false;
                                 break;
                             }
@@ -472,10 +397,11 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("progress: {}%", progress);
+                    reporter.progress("progress", progress);
                     old_progress = progress;
                 }
             }
@@ -500,7 +426,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                 zout_n = output[(row_n, col_n)];
                 if zout_n == background_val {
                     zin_n = input[(row_n, col_n)];
-                    if zin_n != nodata {
+                    if !is_nodata(zin_n, nodata) {
                         flow_dir[(row_n, col_n)] = back_link[n];
 
                         // if zin_n < (zout + small_num) { zin_n = zout + small_num; } // We're in a depression. Raise the elevation.
@@ -540,7 +466,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                         output[(row_n, col_n)] = nodata;
                         num_solved_cells += 1;
                     }
-                } else if zout_n > zout && zout_n != nodata && aspect[(row_n, col_n)] != nodata {
+                } else if zout_n > zout && !is_nodata(zout_n, nodata) && !is_nodata(aspect[(row_n, col_n)], nodata) {
                     /* Check to see if the flow direction could be improved; if so, capture its flow.
                     This is the main logic for the flow direction calculation. Basically,
                     we link cells to the neighbour that has the closest flow direction to the
@@ -576,11 +502,12 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -619,7 +546,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
             let input = input.clone();
             let flow_dir = flow_dir.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            pool.spawn(tid as usize, move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let inflowing_vals: [i8; 8] = [4, 5, 6, 7, 0, 1, 2, 3];
@@ -629,7 +556,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if flow_dir[(row + dy[i], col + dx[i])] == inflowing_vals[i] {
@@ -641,7 +568,9 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                             data[col as usize] = -1i8;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -662,10 +591,11 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -690,11 +620,12 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow accumulation: {}%", progress);
+                    reporter.progress("Flow accumulation", progress);
                     old_progress = progress;
                 }
             }
@@ -727,7 +658,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
         if log_transform {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
+                    if is_nodata(input[(row, col)], nodata) {
                         output[(row, col)] = nodata;
                     } else {
                         let dir = flow_dir[(row, col)];
@@ -743,10 +674,11 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
@@ -754,7 +686,7 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
         } else {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
+                    if is_nodata(input[(row, col)], nodata) {
                         output[(row, col)] = nodata;
                     } else {
                         let dir = flow_dir[(row, col)];
@@ -769,10 +701,11 @@ impl WhiteboxTool for FlowAccumulationFullWorkflow {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
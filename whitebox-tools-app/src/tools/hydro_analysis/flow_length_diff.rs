@@ -55,7 +55,7 @@ impl FlowLengthDiff {
 
         parameters.push(ToolParameter {
             name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -124,47 +124,12 @@ impl WhiteboxTool for FlowLengthDiff {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut d8_file = String::new();
-        let mut output_file = String::new();
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-d8_pntr" || vec[0].to_lowercase() == "--d8_pntr" {
-                if keyval {
-                    d8_file = vec[1].to_string();
-                } else {
-                    d8_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-esri_pntr"
-                || vec[0].to_lowercase() == "--esri_pntr"
-                || vec[0].to_lowercase() == "--esri_style"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("d8_pntr").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let esri_style = parsed.get_bool("esri_pntr");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -177,17 +142,11 @@ impl WhiteboxTool for FlowLengthDiff {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !d8_file.contains(&sep) && !d8_file.contains("/") {
-            d8_file = format!("{}{}", working_directory, d8_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        d8_file = resolve_input_path(&d8_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading pointer data...")
@@ -249,7 +208,7 @@ impl WhiteboxTool for FlowLengthDiff {
         let (mut x, mut y): (isize, isize);
         for row in 0..rows {
             for col in 0..columns {
-                if pntr[(row, col)] >= 0.0 && pntr[(row, col)] != nodata {
+                if pntr[(row, col)] >= 0.0 && !is_nodata(pntr[(row, col)], nodata) {
                     dist = 0f64;
                     flag = false;
                     x = col;
@@ -257,7 +216,7 @@ impl WhiteboxTool for FlowLengthDiff {
                     while !flag {
                         // find its downslope neighbour
                         dir = pntr[(y, x)];
-                        if dir > 0f64 && dir != nodata {
+                        if dir > 0f64 && !is_nodata(dir, nodata) {
                             if dir > 128f64 || pntr_matches[dir as usize] == 999 {
                                 return Err(Error::new(ErrorKind::InvalidInput,
                                     "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools."));
@@ -286,7 +245,7 @@ impl WhiteboxTool for FlowLengthDiff {
 
                         // find its downslope neighbour
                         dir = pntr[(y, x)];
-                        if dir > 0f64 && dir != nodata {
+                        if dir > 0f64 && !is_nodata(dir, nodata) {
                             // move x and y accordingly
                             c = pntr_matches[dir as usize];
                             x += dx[c];
@@ -306,10 +265,11 @@ impl WhiteboxTool for FlowLengthDiff {
                     dfl[(row, col)] = out_nodata;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -325,12 +285,12 @@ impl WhiteboxTool for FlowLengthDiff {
         for row in 0..rows {
             for col in 0..columns {
                 z = dfl[(row, col)];
-                if z != out_nodata {
+                if !is_nodata(z, out_nodata) {
                     max_abs_diff = f64::NEG_INFINITY;
                     // Use 4-neighbour connectedness
                     for n in (0..8).filter(|x| x % 2 == 1) {
                         zn = dfl[(row + dy[n], col + dx[n])];
-                        if zn != out_nodata {
+                        if !is_nodata(zn, out_nodata) {
                             if (z - zn).abs() > max_abs_diff {
                                 max_abs_diff = (z - zn).abs();
                             }
@@ -345,10 +305,11 @@ impl WhiteboxTool for FlowLengthDiff {
                     output[(row, col)] = out_nodata;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,437 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use crate::tools::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+use whitebox_common::structures::{Array2D, HexGrid};
+use whitebox_raster::*;
+
+/// This tool calculates flow accumulation on a hexagonal grid DEM (`--dem`), such as one created from a
+/// square-grid DEM using `SquareToHexGrid`, using the D6 single-flow-direction algorithm, i.e. the flow
+/// from each cell is routed to the lowest of its six neighbours, provided that it is lower than the cell.
+/// Because the six neighbours of a hexagon are equidistant, the lowest neighbour is also the steepest
+/// descent direction, and D6 flow paths are free of the bias toward the cardinal and diagonal directions
+/// that produces the straight, parallel flow paths of D8 on square grids (de Sousa et al., 2006).
+///
+/// Flow terminates at pits and flat areas, and so the DEM must be hydrologically corrected. Because the
+/// depression removal tools operate on square grids, and resampling a corrected DEM onto a hexagonal grid
+/// can create new pits, the tool can fill the depressions of the hexagonal grid itself (`--fill`), using the
+/// priority-flood method of Barnes et al. (2014) with a small gradient imposed across filled areas. The output (`--output`) is a hexagonal grid in the same layout as the input, which can
+/// be converted onto a square grid using `HexToSquareGrid`. The output can be the number of inflowing cells
+/// (`cells`, including the cell itself), the upslope `catchment area`, or the `specific contributing area`,
+/// i.e. the catchment area divided by the length of a hexagon side, which is the flow width. The output may
+/// optionally be log-transformed (`--log`).
+///
+/// # References
+/// Barnes, R., Lehman, C., & Mulla, D. (2014). Priority-flood: An optimal depression-filling and
+/// watershed-labeling algorithm for digital elevation models. *Computers & Geosciences*, 62, 117-127.
+///
+/// de Sousa, L., Nery, F., Sousa, R., & Matos, J. (2006). Assessing the accuracy of hexagonal versus square
+/// tiled grids in preserving DEM surface flow directions. *Proceedings of the 7th International Symposium on
+/// Spatial Accuracy Assessment in Natural Resources and Environmental Sciences*, 191-200.
+///
+/// # See Also
+/// `SquareToHexGrid`, `HexToSquareGrid`, `D8FlowAccumulation`
+pub struct HexFlowAccumulation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl HexFlowAccumulation {
+    pub fn new() -> HexFlowAccumulation {
+        // public constructor
+        let name = "HexFlowAccumulation".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description = "Calculates D6 flow accumulation on a hexagonal grid DEM.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Hexagonal Grid DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input hexagonal grid DEM, e.g. created by SquareToHexGrid.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output hexagonal grid raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter{
+            name: "Output Type".to_owned(), 
+            flags: vec!["--out_type".to_owned()], 
+            description: "Output type; one of 'cells' (default), 'catchment area', and 'specific contributing area'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["cells".to_owned(), "catchment area".to_owned(), "specific contributing area".to_owned()]),
+            default_value: Some("cells".to_owned()),
+            optional: true
+        });
+
+        parameters.push(ToolParameter {
+            name: "Log-transform the output?".to_owned(),
+            flags: vec!["--log".to_owned()],
+            description: "Optional flag to request the output be log-transformed.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Fill depressions?".to_owned(),
+            flags: vec!["--fill".to_owned()],
+            description: "Optional flag to fill the depressions of the hexagonal grid DEM before routing flow.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=dem_hex.tif -o=flow_accum_hex.tif --out_type=\"catchment area\" --fill",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        HexFlowAccumulation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for HexFlowAccumulation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut out_type = String::from("cells");
+        let log_transform = parsed.get_bool("log");
+        let fill = parsed.get_bool("fill");
+
+        if let Some(value) = parsed.get_string("out_type") {
+            out_type = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!(
+                "* Welcome to {} {}*",
+                tool_name,
+                " ".repeat(welcome_len - 15 - tool_name.len())
+            );
+            println!(
+                "* Powered by WhiteboxTools {}*",
+                " ".repeat(welcome_len - 28)
+            );
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Raster::new(&input_file, "r")?;
+        let grid = HexGrid::from_raster_grid(
+            dem.configs.rows,
+            dem.configs.columns,
+            dem.configs.west,
+            dem.configs.north,
+            dem.configs.resolution_x,
+            dem.configs.resolution_y,
+        )
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "The input DEM is not a hexagonal grid; its row spacing should be sqrt(3)/2 times its column spacing. Use SquareToHexGrid to create hexagonal grids.",
+            )
+        })?;
+
+        let start = Instant::now();
+
+        let rows = grid.rows;
+        let columns = grid.columns;
+        let nodata = dem.configs.nodata;
+
+        let mut elev: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for row in 0..rows {
+            elev.set_row_data(row, dem.get_row_data(row));
+        }
+        if fill {
+            // Priority-flood from the cells along the grid edges and the edges of nodata areas,
+            // raising each cell to at least slightly above the cell from which it was reached.
+            let small_num = 1e-6f64;
+            let mut queued: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+            let mut heap = BinaryHeap::new();
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    if is_nodata(elev.get_value(row, col), nodata) {
+                        continue;
+                    }
+                    let is_edge = grid
+                        .neighbours(row, col)
+                        .iter()
+                        .any(|&(r, c)| !grid.is_in_grid(r, c) || is_nodata(elev.get_value(r, c), nodata));
+                    if is_edge {
+                        heap.push(GridCell {
+                            row,
+                            column: col,
+                            priority: elev.get_value(row, col),
+                        });
+                        queued.set_value(row, col, 1u8);
+                    }
+                }
+            }
+            while let Some(cell) = heap.pop() {
+                reporter.check_cancelled()?;
+                let zc = elev.get_value(cell.row, cell.column);
+                for &(r, c) in grid.neighbours(cell.row, cell.column).iter() {
+                    if !grid.is_in_grid(r, c)
+                        || queued.get_value(r, c) == 1u8
+                        || is_nodata(elev.get_value(r, c), nodata)
+                    {
+                        continue;
+                    }
+                    if elev.get_value(r, c) <= zc {
+                        elev.set_value(r, c, zc + small_num);
+                    }
+                    queued.set_value(r, c, 1u8);
+                    heap.push(GridCell {
+                        row: r,
+                        column: c,
+                        priority: elev.get_value(r, c),
+                    });
+                }
+            }
+        }
+
+        // Find the receiving neighbour of each cell and count the inflowing neighbours.
+        let mut receiver: Array2D<isize> = Array2D::new(rows, columns, -1isize, -1isize)?;
+        let mut num_inflowing: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = elev.get_value(row, col);
+                if is_nodata(z, nodata) {
+                    continue;
+                }
+                let mut min_z = z;
+                let mut lowest = -1isize;
+                for &(r, c) in grid.neighbours(row, col).iter() {
+                    if !grid.is_in_grid(r, c) {
+                        continue;
+                    }
+                    let zn = elev.get_value(r, c);
+                    if !is_nodata(zn, nodata) && zn < min_z {
+                        min_z = zn;
+                        lowest = r * columns + c;
+                    }
+                }
+                if lowest >= 0 {
+                    receiver.set_value(row, col, lowest);
+                    num_inflowing.increment(lowest / columns, lowest % columns, 1u8);
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Flow directions", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Accumulate flow downslope, visiting each cell after all of its upslope neighbours.
+        let mut accum: Array2D<f64> = Array2D::new(rows, columns, 1f64, 0f64)?;
+        let mut stack = vec![];
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                if !is_nodata(elev.get_value(row, col), nodata) && num_inflowing.get_value(row, col) == 0 {
+                    stack.push((row, col));
+                }
+            }
+        }
+        let num_cells = dem.num_valid_cells().max(1);
+        let mut num_solved = 0usize;
+        while let Some((row, col)) = stack.pop() {
+            let n = receiver.get_value(row, col);
+            if n >= 0 {
+                let (r, c) = (n / columns, n % columns);
+                accum.increment(r, c, accum.get_value(row, col));
+                num_inflowing.decrement(r, c, 1u8);
+                if num_inflowing.get_value(r, c) == 0 {
+                    stack.push((r, c));
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                num_solved += 1;
+                progress = (100.0_f64 * num_solved as f64 / num_cells as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Flow accumulation", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let multiplier = if out_type.contains("specific") || out_type.contains("sca") {
+            // the flow width is the length of a hexagon side
+            grid.cell_area() / (grid.width / 3f64.sqrt())
+        } else if out_type.contains("catchment") || out_type.contains("area") {
+            grid.cell_area()
+        } else {
+            1f64
+        };
+
+        let mut configs = dem.configs.clone();
+        configs.data_type = DataType::F32;
+        configs.photometric_interp = PhotometricInterpretation::Continuous;
+        configs.palette = "blueyellow.plt".to_string();
+        configs.metadata = vec![];
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        let out_nodata = output.configs.nodata;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                if is_nodata(dem.get_value(row, col), nodata) {
+                    output.set_value(row, col, out_nodata);
+                    continue;
+                }
+                let value = accum.get_value(row, col) * multiplier;
+                output.set_value(row, col, if log_transform { value.ln() } else { value });
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!(
+            "Hexagonal grid: odd-r offset layout, width {}",
+            grid.width
+        ));
+        output.add_metadata_entry(format!("Output type: {}", out_type));
+        output.add_metadata_entry(format!("Log transformed: {}", log_transform));
+        output.add_metadata_entry(format!("Depressions filled: {}", fill));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct GridCell {
+    row: isize,
+    column: isize,
+    priority: f64,
+}
+
+impl Eq for GridCell {}
+
+impl PartialOrd for GridCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.priority.partial_cmp(&self.priority)
+    }
+}
+
+impl Ord for GridCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
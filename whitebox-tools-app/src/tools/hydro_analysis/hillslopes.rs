@@ -81,7 +81,7 @@ impl Hillslopes {
 
         parameters.push(ToolParameter {
             name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -146,54 +146,13 @@ impl WhiteboxTool for Hillslopes {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut d8_file = String::new();
-        let mut streams_file = String::new();
-        let mut output_file = String::new();
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-d8_pntr" || vec[0].to_lowercase() == "--d8_pntr" {
-                if keyval {
-                    d8_file = vec[1].to_string();
-                } else {
-                    d8_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-streams" || vec[0].to_lowercase() == "--streams" {
-                if keyval {
-                    streams_file = vec[1].to_string();
-                } else {
-                    streams_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-esri_pntr"
-                || vec[0].to_lowercase() == "--esri_pntr"
-                || vec[0].to_lowercase() == "--esri_style"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("d8_pntr").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let esri_style = parsed.get_bool("esri_pntr");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -206,20 +165,12 @@ impl WhiteboxTool for Hillslopes {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !d8_file.contains(&sep) && !d8_file.contains("/") {
-            d8_file = format!("{}{}", working_directory, d8_file);
-        }
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        d8_file = resolve_input_path(&d8_file, working_directory)?;
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -240,11 +191,9 @@ impl WhiteboxTool for Hillslopes {
         if streams.configs.rows != pntr.configs.rows
             || streams.configs.columns != pntr.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&streams, &pntr, verbose);
 
         // First assign each stream link a unique identifier
         let mut pourpts: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
@@ -264,7 +213,7 @@ impl WhiteboxTool for Hillslopes {
         let mut current_id = 1f64;
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0.0 && streams[(row, col)] != nodata {
+                if streams[(row, col)] > 0.0 && !is_nodata(streams[(row, col)], nodata) {
                     count = 0i8;
                     for i in 0..8 {
                         if streams[(row + dy[i], col + dx[i])] > 0.0
@@ -282,7 +231,7 @@ impl WhiteboxTool for Hillslopes {
                         current_id += 1f64;
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
+                    if !is_nodata(pntr[(row, col)], pntr_nodata) {
                         pourpts[(row, col)] = 0.0;
                     } else {
                         pourpts[(row, col)] = nodata;
@@ -290,10 +239,11 @@ impl WhiteboxTool for Hillslopes {
                     num_solved_cells += 1;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Processing streams: {}%", progress);
+                    reporter.progress("Processing streams", progress);
                     old_progress = progress;
                 }
             }
@@ -353,7 +303,7 @@ impl WhiteboxTool for Hillslopes {
                 if num_inflowing[(row_n, col_n)] > 1 {
                     current_id += 1f64;
                     pourpts[(row_n, col_n)] = current_id;
-                } else if pourpts[(row_n, col_n)] == nodata {
+                } else if is_nodata(pourpts[(row_n, col_n)], nodata) {
                     pourpts[(row_n, col_n)] = val;
                 }
 
@@ -363,10 +313,11 @@ impl WhiteboxTool for Hillslopes {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Processing streams: {}%", progress);
+                    reporter.progress("Processing streams", progress);
                     old_progress = progress;
                 }
             }
@@ -374,6 +325,7 @@ impl WhiteboxTool for Hillslopes {
 
         // Assign a new unique id to each channel head
         while !heads.is_empty() {
+            reporter.check_cancelled()?;
             let cell = heads.pop().expect("Error during pop operation.");
             row = cell.0;
             col = cell.1;
@@ -392,18 +344,19 @@ impl WhiteboxTool for Hillslopes {
         let mut z: f64;
         for row in 0..rows {
             for col in 0..columns {
-                if pntr[(row, col)] == pntr_nodata {
+                if is_nodata(pntr[(row, col)], pntr_nodata) {
                     output[(row, col)] = nodata;
                 }
                 z = pourpts[(row, col)];
-                if z != nodata && z > 0.0 {
+                if !is_nodata(z, nodata) && z > 0.0 {
                     output[(row, col)] = z;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Watershedding (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Watershedding (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -460,10 +413,11 @@ impl WhiteboxTool for Hillslopes {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Watershedding (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Watershedding (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -472,14 +426,15 @@ impl WhiteboxTool for Hillslopes {
         // Replace all stream cells with 0's
         for row in 0..rows {
             for col in 0..columns {
-                if streams[(row, col)] > 0f64 && streams[(row, col)] != nodata {
+                if streams[(row, col)] > 0f64 && !is_nodata(streams[(row, col)], nodata) {
                     output[(row, col)] = 0f64;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Watershedding (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Watershedding (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -503,7 +458,7 @@ impl WhiteboxTool for Hillslopes {
         for row in 0..rows {
             for col in 0..columns {
                 if visited[(row, col)] > 0
-                    && pntr[(row, col)] != pntr_nodata
+                    && !is_nodata(pntr[(row, col)], pntr_nodata)
                     && output[(row, col)] > 0f64
                 {
                     current_id += 1f64;
@@ -539,10 +494,11 @@ impl WhiteboxTool for Hillslopes {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Grouping hillslopes: {}%", progress);
+                    reporter.progress("Grouping hillslopes", progress);
                     old_progress = progress;
                 }
             }
@@ -180,102 +180,16 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut out_mean_file = String::new();
-        let mut out_max_file = String::new();
-        let mut out_volume_file = String::new();
-        let mut out_area_file = String::new();
-        let mut out_height_file = String::new();
-        let mut dam_length = 111f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_mean" {
-                out_mean_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_max" {
-                out_max_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_volume" {
-                out_volume_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_area" {
-                out_area_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_dam_height" {
-                out_height_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            // } else if flag_val == "-o" || flag_val == "-output" {
-            //     output_file = if keyval {
-            //         vec[1].to_string()
-            //     } else {
-            //         args[i + 1].to_string()
-            //     };
-            // } else if flag_val == "-out_type" {
-            //     let val = if keyval {
-            //         vec[1].to_lowercase()
-            //     } else {
-            //         args[i + 1].to_lowercase()
-            //     };
-            //     out_type = if val.contains("v") {
-            //         1
-            //     } else if val.contains("mean depth") {
-            //         2
-            //     } else if val.contains("max depth") {
-            //         3
-            //     } else {
-            //         0 // area
-            //     };
-            } else if flag_val == "-damlength" {
-                dam_length = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut out_mean_file = parsed.get_string("out_mean").unwrap_or_default();
+        let mut out_max_file = parsed.get_string("out_max").unwrap_or_default();
+        let mut out_volume_file = parsed.get_string("out_volume").unwrap_or_default();
+        let mut out_area_file = parsed.get_string("out_area").unwrap_or_default();
+        let mut out_height_file = parsed.get_string("out_dam_height").unwrap_or_default();
+        let dam_length = parsed.get_f64("damlength").unwrap_or(111f64);
 
         let mut progress: usize;
         let mut old_progress: usize = 1;
@@ -291,26 +205,12 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !out_mean_file.is_empty() && !out_mean_file.contains(&sep) && !out_mean_file.contains("/") {
-            out_mean_file = format!("{}{}", working_directory, out_mean_file);
-        }
-        if !out_max_file.is_empty() && !out_max_file.contains(&sep) && !out_max_file.contains("/") {
-            out_max_file = format!("{}{}", working_directory, out_max_file);
-        }
-        if !out_volume_file.is_empty() && !out_volume_file.contains(&sep) && !out_volume_file.contains("/") {
-            out_volume_file = format!("{}{}", working_directory, out_volume_file);
-        }
-        if !out_area_file.is_empty() && !out_area_file.contains(&sep) && !out_area_file.contains("/") {
-            out_area_file = format!("{}{}", working_directory, out_area_file);
-        }
-        if !out_height_file.is_empty() && !out_height_file.contains(&sep) && !out_height_file.contains("/") {
-            out_height_file = format!("{}{}", working_directory, out_height_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        out_mean_file = resolve_path(&out_mean_file, working_directory);
+        out_max_file = resolve_path(&out_max_file, working_directory);
+        out_volume_file = resolve_path(&out_volume_file, working_directory);
+        out_area_file = resolve_path(&out_area_file, working_directory);
+        out_height_file = resolve_path(&out_height_file, working_directory);
 
         // There must be at least one output.
         let mut num_output = 0;
@@ -396,7 +296,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     for dir in 0..4 {
                         // what's the perpendicular direction?
                         perp_dir1 = perpendicular1[dir];
@@ -412,7 +312,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                             r_n += dy[perp_dir1 as usize];
                             c_n += dx[perp_dir1 as usize];
                             z_n = input.get_value(r_n, c_n);
-                            if z_n != nodata {
+                            if !is_nodata(z_n, nodata) {
                                 dam_profile[half_dam_length + i as usize] = z_n;
                             } else {
                                 dam_profile[half_dam_length + i as usize] = f64::NEG_INFINITY;
@@ -421,7 +321,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                             r_n2 += dy[perp_dir2 as usize];
                             c_n2 += dx[perp_dir2 as usize];
                             z_n = input.get_value(r_n2, c_n2);
-                            if z_n != nodata {
+                            if !is_nodata(z_n, nodata) {
                                 dam_profile[half_dam_length - i] = z_n;
                             } else {
                                 dam_profile[half_dam_length - i] = f64::NEG_INFINITY;
@@ -460,7 +360,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                             r_n += dy[perp_dir1 as usize];
                             c_n += dx[perp_dir1 as usize];
                             z_n = input.get_value(r_n, c_n);
-                            if z_n != nodata {
+                            if !is_nodata(z_n, nodata) {
                                 if dam_profile_filled[half_dam_length + i as usize]
                                     > crest_elev.get_value(r_n, c_n)
                                 {
@@ -475,7 +375,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                             r_n2 += dy[perp_dir2 as usize];
                             c_n2 += dx[perp_dir2 as usize];
                             z_n = input.get_value(r_n2, c_n2);
-                            if z_n != nodata {
+                            if !is_nodata(z_n, nodata) {
                                 if dam_profile_filled[half_dam_length - i as usize]
                                     > crest_elev.get_value(r_n2, c_n2)
                                 {
@@ -492,10 +392,11 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                     crest_elev.set_value(row, col, nodata);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating dam heights: {}%", progress);
+                    reporter.progress("Calculating dam heights", progress);
                     old_progress = progress;
                 }
             }
@@ -534,6 +435,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let mut queue: VecDeque<(isize, isize)> =
             VecDeque::with_capacity((rows * columns) as usize);
         for row in 0..rows {
+            reporter.check_cancelled()?;
             /*
             Note that this is only possible because Whitebox rasters
             allow you to address cells beyond the raster extent but
@@ -573,7 +475,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                 zin_n = input.get_value(row_n, col_n);
                 zout_n = filled_dem.get_value(row_n, col_n);
                 if zout_n == background_val {
-                    if zin_n == nodata {
+                    if is_nodata(zin_n, nodata) {
                         filled_dem.set_value(row_n, col_n, nodata);
                         queue.push_back((row_n, col_n));
                         num_nodata_cells += 1;
@@ -591,10 +493,11 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating flow directions: {}%", progress);
+                    reporter.progress("Calculating flow directions", progress);
                     old_progress = progress;
                 }
             }
@@ -619,7 +522,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                 zout_n = filled_dem.get_value(row_n, col_n);
                 if zout_n == background_val {
                     zin_n = crest_elev.get_value(row_n, col_n);
-                    if zin_n != nodata {
+                    if !is_nodata(zin_n, nodata) {
                         flow_dir.set_value(row_n, col_n, back_link[n]);
                         count += 1;
                         if zin_n < zout {
@@ -644,11 +547,12 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                 stack.push((row, col));
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating flow directions: {}%", progress);
+                    reporter.progress("Calculating flow directions", progress);
                     old_progress = progress;
                 }
             }
@@ -759,17 +663,17 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating index: {}%", progress);
+                    reporter.progress("Calculating index", progress);
                     old_progress = progress;
                 }
             }
         }
 
-
         let elapsed_time = get_formatted_elapsed_time(start);
 
         // Output the dam height above the ground elevation
@@ -787,7 +691,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
             for row in 0..rows {
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         output_hgt.set_value(row, col, crest_elev.get_value(row, col) - z);
 
                         if output_hgt.get_value(row, col) <= 0f64 {
@@ -802,10 +706,11 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                     //     output.set_value(row, col, nodata);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Outputting dam heights: {}%", progress);
+                        reporter.progress("Outputting dam heights", progress);
                         old_progress = progress;
                     }
                 }
@@ -841,7 +746,7 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         let mut vol_f32: f32;
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) != nodata {
+                if !is_nodata(input.get_value(row, col), nodata) {
                     area = out_area.get_value(row, col);
                     if area != 0f32 {
                         vol_f32 = out_volume.get_value(row, col);
@@ -855,10 +760,11 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating mean depths: {}%", progress);
+                    reporter.progress("Calculating mean depths", progress);
                     old_progress = progress;
                 }
             }
@@ -944,10 +850,6 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
             };
         }
 
-
-
-        
-        
         // output.configs.palette = "spectrum.plt".to_string();
         // output.add_metadata_entry(format!(
         //     "Created by whitebox_tools\' {} tool",
@@ -976,8 +878,6 @@ impl WhiteboxTool for ImpoundmentSizeIndex {
         //     Err(e) => return Err(e),
         // };
 
-        
-
         if verbose {
             println!(
                 "{}",
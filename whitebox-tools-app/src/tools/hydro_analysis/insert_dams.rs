@@ -11,7 +11,7 @@ use crate::tools::*;
 use whitebox_vector::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to insert dams at one or more user-specified points (`--dam_pts`), and of a maximum length
@@ -145,60 +145,13 @@ impl WhiteboxTool for InsertDams {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut dam_file = String::new();
-        let mut dam_length = 111f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-dam_pts" {
-                dam_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-damlength" {
-                dam_length = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut dam_file = parsed.get_string("dam_pts").unwrap_or_default();
+        let dam_length = parsed.get_f64("damlength").unwrap_or(111f64);
 
         let mut progress: usize;
         let mut old_progress: usize = 1;
@@ -214,17 +167,9 @@ impl WhiteboxTool for InsertDams {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
-        if !dam_file.contains(&sep) && !dam_file.contains("/") {
-            dam_file = format!("{}{}", working_directory, dam_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        dam_file = resolve_input_path(&dam_file, working_directory)?;
 
         if verbose {
             println!("Reading data...")
@@ -289,7 +234,7 @@ impl WhiteboxTool for InsertDams {
                     ..=(target_col + half_dam_length as isize)
                 {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         // dam_z = z;
                         for dir in 0..4 {
                             profile_intersects_target = false;
@@ -313,7 +258,7 @@ impl WhiteboxTool for InsertDams {
                                     target_cell = half_dam_length + i as usize;
                                 }
                                 z_n = input.get_value(r_n, c_n);
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     dam_profile[half_dam_length + i as usize] = z_n;
                                 } else {
                                     dam_profile[half_dam_length + i as usize] = f64::NEG_INFINITY;
@@ -326,7 +271,7 @@ impl WhiteboxTool for InsertDams {
                                     target_cell = half_dam_length - i as usize;
                                 }
                                 z_n = input.get_value(r_n2, c_n2);
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     dam_profile[half_dam_length - i] = z_n;
                                 } else {
                                     dam_profile[half_dam_length - i] = f64::NEG_INFINITY;
@@ -393,7 +338,7 @@ impl WhiteboxTool for InsertDams {
                     r_n += dy[perp_dir1 as usize];
                     c_n += dx[perp_dir1 as usize];
                     z_n = input.get_value(r_n, c_n);
-                    if z_n != nodata {
+                    if !is_nodata(z_n, nodata) {
                         if best_dam_profile_filled[half_dam_length + i as usize]
                             > output.get_value(r_n, c_n)
                         {
@@ -420,7 +365,7 @@ impl WhiteboxTool for InsertDams {
                     r_n2 += dy[perp_dir2 as usize];
                     c_n2 += dx[perp_dir2 as usize];
                     z_n = input.get_value(r_n2, c_n2);
-                    if z_n != nodata {
+                    if !is_nodata(z_n, nodata) {
                         if best_dam_profile_filled[half_dam_length - i as usize]
                             > output.get_value(r_n2, c_n2)
                         {
@@ -453,11 +398,12 @@ impl WhiteboxTool for InsertDams {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (dam_pts.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -9,7 +9,6 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs::File;
@@ -18,7 +17,7 @@ use std::io::{BufWriter, Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to divide a landscape into a group of nearly equal-sized watersheds, known as *isobasins*.
 /// The user must specify the name (`--dem`) of a digital elevation model (DEM), the output raster name (`--output`),
@@ -55,7 +54,7 @@ impl Isobasins {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -151,52 +150,13 @@ impl WhiteboxTool for Isobasins {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut target_size = -1;
-        let mut output_connections = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-size" {
-                target_size = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-connections" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    output_connections = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let target_size = parsed.get_i64("size").map(|v| v as isize).unwrap_or(-1);
+        let output_connections = parsed.get_bool("connections");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -218,17 +178,11 @@ impl WhiteboxTool for Isobasins {
 
         let target_fa = target_size as usize;
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -248,17 +202,13 @@ impl WhiteboxTool for Isobasins {
         let cell_size_y = input.configs.resolution_y;
         let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
         let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let nodata = input.configs.nodata;
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
@@ -281,13 +231,13 @@ impl WhiteboxTool for Isobasins {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
                                 z_n = input[(row + dy[i], col + dx[i])];
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -309,7 +259,9 @@ impl WhiteboxTool for Isobasins {
                             data[col as usize] = -1i8;
                         }
                     }
-                    tx.send((row, data, interior_pit_found)).unwrap();
+                    if tx.send((row, data, interior_pit_found)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -321,10 +273,11 @@ impl WhiteboxTool for Isobasins {
             if pit {
                 interior_pit_found = true;
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow directions: {}%", progress);
+                    reporter.progress("Flow directions", progress);
                     old_progress = progress;
                 }
             }
@@ -344,7 +297,7 @@ impl WhiteboxTool for Isobasins {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     count = 0i8;
                     for i in 0..8 {
                         if flow_dir.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i] {
@@ -360,16 +313,16 @@ impl WhiteboxTool for Isobasins {
                     num_solved_cells += 1;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
         }
 
-
         /////////////////////////////////
         // Find and ID the pour points //
         /////////////////////////////////
@@ -449,7 +402,7 @@ impl WhiteboxTool for Isobasins {
                     stack.push((row_n, col_n));
                 }
             } else {
-                if output.get_value(row, col) == out_nodata {
+                if is_nodata(output.get_value(row, col), out_nodata) {
                     output.set_value(row, col, outlet_id);
                     outlet_id += 1f64;
                     outlet_row.push(row);
@@ -458,11 +411,12 @@ impl WhiteboxTool for Isobasins {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding pour points: {}%", progress);
+                    reporter.progress("Finding pour points", progress);
                     old_progress = progress;
                 }
             }
@@ -478,7 +432,7 @@ impl WhiteboxTool for Isobasins {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata && output.get_value(row, col) == out_nodata {
+                if !is_nodata(z, nodata) && is_nodata(output.get_value(row, col), out_nodata) {
                     // trace the flowpath from this cell until you find an outlet ID in the output
                     outlet_id = nodata;
                     flag = true;
@@ -494,7 +448,7 @@ impl WhiteboxTool for Isobasins {
 
                             // if the new cell already has a value in the output, use that as the outletID
                             z = output.get_value(row_n, col_n);
-                            if z != out_nodata {
+                            if !is_nodata(z, out_nodata) {
                                 outlet_id = z;
                                 flag = false;
                             }
@@ -517,7 +471,7 @@ impl WhiteboxTool for Isobasins {
 
                             // if the new cell already has a value in the output, use that as the outletID
                             z = output.get_value(row_n, col_n);
-                            if z != out_nodata {
+                            if !is_nodata(z, out_nodata) {
                                 outlet_id = z;
                                 flag = false;
                             }
@@ -528,10 +482,11 @@ impl WhiteboxTool for Isobasins {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Labelling basins: {}%", progress);
+                    reporter.progress("Labelling basins", progress);
                     old_progress = progress;
                 }
             }
@@ -545,10 +500,10 @@ impl WhiteboxTool for Isobasins {
             for row in 0..rows {
                 for col in 0..columns {
                     z = output.get_value(row, col);
-                    if z != out_nodata {
+                    if !is_nodata(z, out_nodata) {
                         for i in 0..8 {
                             z_n = output.get_value(row + dy[i], col + dx[i]);
-                            if z_n != z && z_n != out_nodata {
+                            if z_n != z && !is_nodata(z_n, out_nodata) {
                                 // neighbouring cell is in a different basin
                                 if flow_dir.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i]
                                 {
@@ -559,10 +514,11 @@ impl WhiteboxTool for Isobasins {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Labelling basins connections: {}%", progress);
+                        reporter.progress("Labelling basins connections", progress);
                         old_progress = progress;
                     }
                 }
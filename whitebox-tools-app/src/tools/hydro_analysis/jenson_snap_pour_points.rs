@@ -156,60 +156,13 @@ impl WhiteboxTool for JensonSnapPourPoints {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut pourpts_file = String::new();
-        let mut streams_file = String::new();
-        let mut output_file = String::new();
-        let mut snap_dist = 0.0;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-pour_pts" {
-                pourpts_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-streams" {
-                streams_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-snap_dist" {
-                snap_dist = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut pourpts_file = parsed.get_string("pour_pts").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let snap_dist = parsed.get_f64("snap_dist").unwrap_or(0.0);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -222,20 +175,12 @@ impl WhiteboxTool for JensonSnapPourPoints {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !pourpts_file.contains(&sep) && !pourpts_file.contains("/") {
-            pourpts_file = format!("{}{}", working_directory, pourpts_file);
-        }
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        pourpts_file = resolve_input_path(&pourpts_file, working_directory)?;
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -284,7 +229,7 @@ impl WhiteboxTool for JensonSnapPourPoints {
             for c in (col - snap_dist_int)..(col + snap_dist_int + 1) {
                 for r in (row - snap_dist_int)..(row + snap_dist_int + 1) {
                     zn = streams.get_value(r, c);
-                    if zn > 0f64 && zn != nodata {
+                    if zn > 0f64 && !is_nodata(zn, nodata) {
                         // it's a stream
                         x = streams.get_x_from_column(c);
                         y = streams.get_y_from_row(r);
@@ -299,11 +244,12 @@ impl WhiteboxTool for JensonSnapPourPoints {
                 }
             }
             output.add_point_record(xn, yn);
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (pourpts.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
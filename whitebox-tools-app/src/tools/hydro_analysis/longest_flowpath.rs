@@ -11,14 +11,13 @@ use whitebox_common::structures::{Array2D, Point2D};
 use crate::tools::*;
 use whitebox_vector::ShapefileGeometry;
 use whitebox_vector::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool delineates the longest flowpaths for a group of subbasins or watersheds.
 /// Flowpaths are initiated along drainage divides and continue along the D8-defined
@@ -66,7 +65,7 @@ impl LongestFlowpath {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -75,7 +74,7 @@ impl LongestFlowpath {
 
         parameters.push(ToolParameter {
             name: "Basins File".to_owned(),
-            flags: vec!["--basins".to_owned()],
+            flags: vec!["--basin".to_owned(), "--basins".to_owned()],
             description: "Input raster basins file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -154,47 +153,12 @@ impl WhiteboxTool for LongestFlowpath {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut basins_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-dem" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-basin" || flag_val == "-basins" {
-                basins_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut basins_file = parsed.get_string("basins").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -207,20 +171,12 @@ impl WhiteboxTool for LongestFlowpath {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !basins_file.contains(&sep) && !basins_file.contains("/") {
-            basins_file = format!("{}{}", working_directory, basins_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        basins_file = resolve_input_path(&basins_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -232,11 +188,9 @@ impl WhiteboxTool for LongestFlowpath {
         if input.configs.rows != basins.configs.rows
             || input.configs.columns != basins.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input rasters must have the same spatial extent (i.e. number of rows and columns).",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input rasters must have the same spatial extent (i.e. number of rows and columns)."));
         }
+        warn_if_crs_differs(&input, &basins, verbose);
 
         // calculate the flow direction
         let start = Instant::now();
@@ -252,18 +206,14 @@ impl WhiteboxTool for LongestFlowpath {
 
         let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let basins = basins.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let nodata = input.configs.nodata;
                 let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
                 let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
@@ -286,13 +236,13 @@ impl WhiteboxTool for LongestFlowpath {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata && basins.get_value(row, col) != basin_nodata {
+                        if !is_nodata(z, nodata) && !is_nodata(basins.get_value(row, col), basin_nodata) {
                             dir = 0i8;
                             max_slope = f64::MIN;
                             neighbouring_nodata = false;
                             for i in 0..8 {
                                 z_n = input.get_value(row + d_y[i], col + d_x[i]);
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     slope = (z - z_n) / grid_lengths[i];
                                     if slope > max_slope && slope > 0f64 {
                                         max_slope = slope;
@@ -311,7 +261,9 @@ impl WhiteboxTool for LongestFlowpath {
                             }
                         }
                     }
-                    tx.send((row, data, interior_pit_found)).unwrap();
+                    if tx.send((row, data, interior_pit_found)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -323,10 +275,11 @@ impl WhiteboxTool for LongestFlowpath {
             if pit {
                 interior_pit_found = true;
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow directions: {}%", progress);
+                    reporter.progress("Flow directions", progress);
                     old_progress = progress;
                 }
             }
@@ -341,7 +294,7 @@ impl WhiteboxTool for LongestFlowpath {
             let input = input.clone();
             let flow_dir = flow_dir.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
                 let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let inflowing_vals: [i8; 8] = [4, 5, 6, 7, 0, 1, 2, 3];
@@ -351,7 +304,7 @@ impl WhiteboxTool for LongestFlowpath {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if flow_dir.get_value(row + d_y[i], col + d_x[i])
@@ -365,7 +318,9 @@ impl WhiteboxTool for LongestFlowpath {
                             data[col as usize] = -1i8;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -386,10 +341,11 @@ impl WhiteboxTool for LongestFlowpath {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -447,11 +403,12 @@ impl WhiteboxTool for LongestFlowpath {
                 list_of_basins.push(row * columns + col);
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Measuring flowpath length: {}%", progress);
+                    reporter.progress("Measuring flowpath length", progress);
                     old_progress = progress;
                 }
             }
@@ -536,7 +493,7 @@ impl WhiteboxTool for LongestFlowpath {
             first_cell_encountered = false;
             flag = true;
             while flag {
-                if input.get_value(row, col) != nodata {
+                if !is_nodata(input.get_value(row, col), nodata) {
                     dir = flow_dir.get_value(row, col);
                     already_added_point = if dir != prev_dir || !first_cell_encountered {
                         prev_dir = dir;
@@ -573,7 +530,7 @@ impl WhiteboxTool for LongestFlowpath {
                             if dir >= 0i8 {
                                 row_n = row_n + d_y[dir as usize];
                                 col_n = col_n + d_x[dir as usize];
-                                if basins.get_value(row_n, col_n) != basin_nodata {
+                                if !is_nodata(basins.get_value(row_n, col_n), basin_nodata) {
                                     x = input.get_x_from_column(col_n);
                                     y = input.get_y_from_row(row_n);
                                     points.push(Point2D::new(x, y));
@@ -622,10 +579,11 @@ impl WhiteboxTool for LongestFlowpath {
                 current_id += 1;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Vectorizing flowpaths: {}%", progress);
+                    reporter.progress("Vectorizing flowpaths", progress);
                     old_progress = progress;
                 }
             }
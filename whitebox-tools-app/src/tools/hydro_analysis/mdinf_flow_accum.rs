@@ -14,15 +14,13 @@ https://github.com/jblindsay/whitebox-geospatial-analysis-tools/blob/master/Geas
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool is used to generate a flow accumulation grid (i.e. contributing area) using the MD-infinity algorithm
 /// (Seibert and McGlynn, 2007). This algorithm is an examples of a multiple-flow-direction (MFD) method because the flow entering
@@ -72,7 +70,7 @@ impl MDInfFlowAccumulation {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -194,91 +192,31 @@ impl WhiteboxTool for MDInfFlowAccumulation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut out_type = String::from("sca");
-        let mut exponent = 1.1;
+        let exponent = parsed.get_f64("exponent").unwrap_or(1.1);
         let mut convergence_threshold = f64::INFINITY;
-        let mut log_transform = false;
-        let mut clip_max = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        let log_transform = parsed.get_bool("log");
+        let clip_max = parsed.get_bool("clip");
+
+        if let Some(value) = parsed.get_string("out_type") {
+            out_type = value.to_lowercase();
+            out_type = if out_type.contains("specific") || out_type.contains("sca") {
+                String::from("sca")
+            } else if out_type.contains("cells") {
+                String::from("cells")
+            } else {
+                String::from("ca")
+            };
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_type" {
-                out_type = if keyval {
-                    vec[1].to_lowercase()
-                } else {
-                    args[i + 1].to_lowercase()
-                };
-                out_type = if out_type.contains("specific") || out_type.contains("sca") {
-                    String::from("sca")
-                } else if out_type.contains("cells") {
-                    String::from("cells")
-                } else {
-                    String::from("ca")
-                };
-            } else if flag_val == "-exponent" {
-                exponent = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-threshold" {
-                convergence_threshold = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if convergence_threshold == 0f64 {
-                    convergence_threshold = f64::INFINITY;
-                }
-            } else if flag_val == "-log" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    log_transform = true;
-                }
-            } else if flag_val == "-clip" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    clip_max = true;
-                }
+        if let Some(value) = parsed.get_f64("threshold") {
+            convergence_threshold = value;
+            if convergence_threshold == 0f64 {
+                convergence_threshold = f64::INFINITY;
             }
         }
 
@@ -293,17 +231,11 @@ impl WhiteboxTool for MDInfFlowAccumulation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -323,17 +255,14 @@ impl WhiteboxTool for MDInfFlowAccumulation {
 
         // calculate the number of inflowing cells
         let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let pool = whitebox_common::utils::ThreadPool::new(&configs);
+        let num_procs = pool.num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            pool.spawn(tid as usize, move || {
                 let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
                 let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let mut z: f64;
@@ -343,7 +272,7 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             count = 0i8;
                             for i in 0..8 {
                                 if input.get_value(row + d_y[i], col + d_x[i]) > z {
@@ -356,7 +285,9 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                             }
                         }
                     }
-                    tx.send((row, data, interior_pit_found)).unwrap();
+                    if tx.send((row, data, interior_pit_found)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -380,10 +311,11 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -452,10 +384,10 @@ impl WhiteboxTool for MDInfFlowAccumulation {
 
                     p1 = input.get_value(row + yd[i], col + xd[i]);
                     p2 = input.get_value(row + yd[ii], col + xd[ii]);
-                    if p1 < z && p1 != nodata {
+                    if p1 < z && !is_nodata(p1, nodata) {
                         downslope[c] = true;
                     }
-                    if p1 != nodata && p2 != nodata {
+                    if !is_nodata(p1, nodata) && !is_nodata(p2, nodata) {
                         // Calculate the elevation difference between the center point and the points p1 and p2
                         z1 = p1 - z;
                         z2 = p2 - z;
@@ -497,7 +429,7 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                         r_facet[c] = hr;
                         s_facet[c] = hs;
                     } else {
-                        if p1 != nodata && p1 < z {
+                        if !is_nodata(p1, nodata) && p1 < z {
                             hr = (i as f64) / 4.0 * PI;
                             hs = (z - p1) / (dd[ii] * grid_res);
 
@@ -522,14 +454,14 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                         } else if r_facet[i] == r_facet[ii] {
                             // If two adjacent triangular facets have the same downslope direction
                             s_facet[i]
-                        } else if (s_facet[ii] == nodata)
+                        } else if (is_nodata(s_facet[ii], nodata))
                             && (r_facet[i] == ((i + 1) as f64 * quarter_pi))
                         {
                             // If the downslope direction is on the border of the current triangular facet, and the corresponding neigbour's downslope is NoData
                             s_facet[i]
                         } else {
                             ii = (i + 7) % 8;
-                            if (s_facet[ii] == nodata) && (r_facet[i] == (i as f64 * quarter_pi)) {
+                            if (is_nodata(s_facet[ii], nodata)) && (r_facet[i] == (i as f64 * quarter_pi)) {
                                 // If the downslope direction is on the other border of the current triangular facet, and the corresponding neigbour's downslope is NoData
                                 s_facet[i]
                             } else {
@@ -604,7 +536,7 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                 max_slope = f64::MIN;
                 for i in 0..8 {
                     z_n = input.get_value(row + d_y[i], col + d_x[i]);
-                    if z_n != nodata {
+                    if !is_nodata(z_n, nodata) {
                         slope = (z - z_n) / grid_lengths[i];
                         if slope > 0f64 {
                             downslope[i] = true;
@@ -635,11 +567,12 @@ impl WhiteboxTool for MDInfFlowAccumulation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved_cells += 1;
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow accumulation: {}%", progress);
+                    reporter.progress("Flow accumulation", progress);
                     old_progress = progress;
                 }
             }
@@ -657,17 +590,18 @@ impl WhiteboxTool for MDInfFlowAccumulation {
         if log_transform {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
+                    if is_nodata(input[(row, col)], nodata) {
                         output[(row, col)] = nodata;
                     } else {
                         output[(row, col)] = (output[(row, col)] * cell_area / avg_cell_size).ln();
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
@@ -675,17 +609,18 @@ impl WhiteboxTool for MDInfFlowAccumulation {
         } else {
             for row in 0..rows {
                 for col in 0..columns {
-                    if input[(row, col)] == nodata {
+                    if is_nodata(input[(row, col)], nodata) {
                         output[(row, col)] = nodata;
                     } else {
                         output[(row, col)] = output[(row, col)] * cell_area / avg_cell_size;
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Correcting values: {}%", progress);
+                        reporter.progress("Correcting values", progress);
                         old_progress = progress;
                     }
                 }
@@ -30,6 +30,7 @@ mod flatten_lakes;
 mod flood_order;
 mod flow_accum_full_workflow;
 mod flow_length_diff;
+mod hex_flow_accumulation;
 mod hillslopes;
 mod impoundment_index;
 mod insert_dams;
@@ -40,12 +41,15 @@ mod max_upslope_flowpath;
 mod mdinf_flow_accum;
 mod num_inflowing_neighbours;
 mod raise_walls;
+mod relative_elevation_model;
 mod rho8_pointer;
 mod sink;
 mod snap_pour_points;
+mod soil_moisture_downscaling;
 mod stochastic_depression_analysis;
 mod strahler_basins;
 mod subbasins;
+mod topo_to_raster;
 mod trace_downslope_flowpaths;
 mod unnest_basins;
 mod upslope_depression_storage;
@@ -83,6 +87,7 @@ pub use self::flatten_lakes::FlattenLakes;
 pub use self::flood_order::FloodOrder;
 pub use self::flow_accum_full_workflow::FlowAccumulationFullWorkflow;
 pub use self::flow_length_diff::FlowLengthDiff;
+pub use self::hex_flow_accumulation::HexFlowAccumulation;
 pub use self::hillslopes::Hillslopes;
 pub use self::impoundment_index::ImpoundmentSizeIndex;
 pub use self::insert_dams::InsertDams;
@@ -93,12 +98,15 @@ pub use self::max_upslope_flowpath::MaxUpslopeFlowpathLength;
 pub use self::mdinf_flow_accum::MDInfFlowAccumulation;
 pub use self::num_inflowing_neighbours::NumInflowingNeighbours;
 pub use self::raise_walls::RaiseWalls;
+pub use self::relative_elevation_model::RelativeElevationModel;
 pub use self::rho8_pointer::Rho8Pointer;
 pub use self::sink::Sink;
 pub use self::snap_pour_points::SnapPourPoints;
+pub use self::soil_moisture_downscaling::SoilMoistureDownscaling;
 pub use self::stochastic_depression_analysis::StochasticDepressionAnalysis;
 pub use self::strahler_basins::StrahlerOrderBasins;
 pub use self::subbasins::Subbasins;
+pub use self::topo_to_raster::TopoToRaster;
 pub use self::trace_downslope_flowpaths::TraceDownslopeFlowpaths;
 pub use self::unnest_basins::UnnestBasins;
 pub use self::upslope_depression_storage::UpslopeDepressionStorage;
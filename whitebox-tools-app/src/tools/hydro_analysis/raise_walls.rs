@@ -146,67 +146,14 @@ impl WhiteboxTool for RaiseWalls {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut breach_file = String::new();
-        let mut dem_file = String::new();
-        let mut output_file = String::new();
-        let mut wall_height = 100f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-walls" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-breach" {
-                breach_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-dem" {
-                dem_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-height" {
-                wall_height = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut breach_file = parsed.get_string("breach").unwrap_or_default();
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let wall_height = parsed.get_f64("height").unwrap_or(100f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -219,20 +166,12 @@ impl WhiteboxTool for RaiseWalls {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !dem_file.contains(&sep) && !dem_file.contains("/") {
-            dem_file = format!("{}{}", working_directory, dem_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -360,7 +299,7 @@ impl WhiteboxTool for RaiseWalls {
                                     x_prime = x1 + (row_y_coord - y1) / (y2 - y1) * (x2 - x1);
                                     let col = output.get_column_from_x(x_prime);
                                     z = output.get_value(row, col);
-                                    if z != nodata && walled.get_value(row, col) == 0u8 {
+                                    if !is_nodata(z, nodata) && walled.get_value(row, col) == 0u8 {
                                         output.set_value(row, col, z + wall_height);
                                         output_something = true;
                                         walled.set_value(row, col, 1u8);
@@ -387,7 +326,7 @@ impl WhiteboxTool for RaiseWalls {
                                     let row = output.get_row_from_y(y_prime);
 
                                     z = output.get_value(row, col);
-                                    if z != nodata && walled.get_value(row, col) == 0u8 {
+                                    if !is_nodata(z, nodata) && walled.get_value(row, col) == 0u8 {
                                         output.set_value(row, col, z + wall_height);
                                         output_something = true;
                                         walled.set_value(row, col, 1u8);
@@ -398,15 +337,11 @@ impl WhiteboxTool for RaiseWalls {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                 if progress != old_progress {
-                    println!(
-                        "Rasterizing {} of {}: {}%",
-                        record_num + 1,
-                        num_records,
-                        progress
-                    );
+                    reporter.progress(&format!("Rasterizing {} of {}", record_num + 1, num_records), progress);
                     old_progress = progress;
                 }
             }
@@ -441,10 +376,11 @@ impl WhiteboxTool for RaiseWalls {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -452,9 +388,7 @@ impl WhiteboxTool for RaiseWalls {
 
         // If breach lines are provided, breach the wall along the lines
         if !breach_file.trim().is_empty() {
-            if !breach_file.contains(&sep) && !breach_file.contains("/") {
-                breach_file = format!("{}{}", working_directory, breach_file);
-            }
+            breach_file = resolve_input_path(&breach_file, working_directory)?;
 
             let breach_data = Shapefile::read(&breach_file)?;
 
@@ -587,15 +521,11 @@ impl WhiteboxTool for RaiseWalls {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Rasterizing {} of {}: {}%",
-                            record_num + 1,
-                            num_records,
-                            progress
-                        );
+                        reporter.progress(&format!("Rasterizing {} of {}", record_num + 1, num_records), progress);
                         old_progress = progress;
                     }
                 }
@@ -0,0 +1,502 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use whitebox_common::structures::{Basis, RadialBasisFunction};
+use crate::tools::*;
+use whitebox_vector::{ShapeType, Shapefile};
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use nalgebra::DVector;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool creates a relative elevation model (REM), i.e. a digital elevation model (`--dem`) that has been
+/// detrended relative to the water surface of a river. REMs remove the regional down-valley slope from the
+/// topography so that the elevation of each grid cell is expressed as a height above the nearby channel. They
+/// are widely used to visualize floodplain features, such as abandoned channels, terraces, levees, and
+/// meander scrolls, and as a simple means of preliminary floodplain and inundation mapping.
+///
+/// The user must specify a vector line file (`--centerline`) that traces the river centerline. The DEM is
+/// sampled along each centerline at a regular spacing (`--spacing`; by default the DEM grid resolution) to
+/// estimate the water surface elevation. Because DEM elevations along a centerline are often noisy, e.g. due to
+/// bridges, vegetation, or misregistration of the line, the samples may optionally be smoothed with a running
+/// median filter along each line (`--smoothing`, the filter size in samples). The sampled water-surface
+/// elevations are then interpolated to every grid cell to create a trend surface, using either inverse-distance
+/// weighting (`--method=idw`, with the distance weight given by `--weight`) or a thin-plate spline
+/// (`--method=spline`), based on the `--num_points` nearest samples. The REM (`--output`) is the difference
+/// between the DEM and the trend surface. The trend surface itself may optionally be output (`--out_trend`).
+///
+/// Interpolating the water surface far from the channel is rarely meaningful, and the user may therefore
+/// specify a maximum distance (`--max_dist`) beyond which output grid cells are assigned the nodata value. The
+/// DEM should be in a projected coordinate system, and the centerline should follow the channel closely; where
+/// a DEM is hydro-flattened, digitizing the centerline along the flattened water surface gives the cleanest
+/// result.
+///
+/// # See Also
+/// `ElevationAboveStream`, `ElevationAboveStreamEuclidean`, `RadialBasisFunctionInterpolation`, `IdwInterpolation`
+pub struct RelativeElevationModel {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl RelativeElevationModel {
+    pub fn new() -> RelativeElevationModel {
+        // public constructor
+        let name = "RelativeElevationModel".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description = "Detrends a DEM relative to the water surface interpolated along a river centerline.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input River Centerline File".to_owned(),
+            flags: vec!["--centerline".to_owned()],
+            description: "Input vector river centerline file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output relative elevation model raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Trend Surface File (optional)".to_owned(),
+            flags: vec!["--out_trend".to_owned()],
+            description: "Output water-surface trend raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Interpolation Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Trend surface interpolation method; options are 'idw' and 'spline'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["idw".to_owned(), "spline".to_owned()]),
+            default_value: Some("idw".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Points".to_owned(),
+            flags: vec!["--num_points".to_owned()],
+            description: "Number of nearest centerline samples used to estimate the trend surface.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("12".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "IDW Weight (Exponent) Value".to_owned(),
+            flags: vec!["--weight".to_owned()],
+            description: "IDW weight value.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Sample Spacing (optional)".to_owned(),
+            flags: vec!["--spacing".to_owned()],
+            description: "Spacing of centerline samples, in map units; defaults to the grid resolution.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Smoothing Filter Size (samples)".to_owned(),
+            flags: vec!["--smoothing".to_owned()],
+            description: "Size of the running median filter applied to centerline samples; 1 for no smoothing.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Distance (optional)".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Maximum distance from the centerline, in map units, of output cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif --centerline=river.shp -o=rem.tif
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif --centerline=river.shp -o=rem.tif --out_trend=water_surface.tif --method=spline --num_points=16 --smoothing=11 --max_dist=2000.0", short_exe, name).replace("*", &sep);
+
+        RelativeElevationModel {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for RelativeElevationModel {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut centerline_file = parsed.get_string("centerline").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut trend_file = parsed.get_string("out_trend").unwrap_or_default();
+        let mut use_spline = false;
+        let mut num_points = parsed.get_usize("num_points")?.unwrap_or(12usize);
+        let weight = parsed.get_f64("weight").unwrap_or(2f64);
+        let mut spacing = parsed.get_f64("spacing").unwrap_or(0f64);
+        let mut smoothing = parsed.get_usize("smoothing")?.unwrap_or(1usize);
+        let max_dist = parsed.get_f64("max_dist").unwrap_or(0f64);
+
+        if let Some(value) = parsed.get_string("method") {
+            let method = value.to_string().to_lowercase();
+            use_spline = method.contains("spline");
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        centerline_file = resolve_input_path(&centerline_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        trend_file = resolve_path(&trend_file, working_directory);
+        if num_points < 1 {
+            num_points = 1;
+        }
+        if smoothing < 1 {
+            smoothing = 1;
+        }
+        if smoothing % 2 == 0 {
+            smoothing += 1;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Arc::new(Raster::new(&dem_file, "r")?);
+        let centerline = Shapefile::read(&centerline_file)?;
+
+        let start = Instant::now();
+
+        if centerline.header.shape_type.base_shape_type() != ShapeType::PolyLine {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input centerline vector data must be of POLYLINE base shape type.",
+            ));
+        }
+
+        let rows = dem.configs.rows as isize;
+        let columns = dem.configs.columns as isize;
+        let nodata = dem.configs.nodata;
+        if spacing <= 0f64 {
+            spacing = (dem.configs.resolution_x + dem.configs.resolution_y) / 2f64;
+        }
+
+        // Sample the DEM along each centerline part.
+        let mut samples: Vec<(f64, f64, f64)> = vec![];
+        for record_num in 0..centerline.num_records {
+            let record = centerline.get_record(record_num);
+            for part in 0..record.num_parts as usize {
+                let part_start = record.parts[part] as usize;
+                let part_end = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize - 1
+                } else {
+                    record.num_points as usize - 1
+                };
+                let mut part_samples: Vec<(f64, f64, f64)> = vec![];
+                let mut dist_to_next = 0f64;
+                for i in part_start..part_end {
+                    let (x1, y1) = (record.points[i].x, record.points[i].y);
+                    let (x2, y2) = (record.points[i + 1].x, record.points[i + 1].y);
+                    let seg_length = ((x2 - x1) * (x2 - x1) + (y2 - y1) * (y2 - y1)).sqrt();
+                    let mut d = dist_to_next;
+                    while d <= seg_length {
+                        let x = x1 + (x2 - x1) * d / seg_length.max(f64::EPSILON);
+                        let y = y1 + (y2 - y1) * d / seg_length.max(f64::EPSILON);
+                        let z = dem.get_value(dem.get_row_from_y(y), dem.get_column_from_x(x));
+                        if !dem.is_nodata(z) {
+                            part_samples.push((x, y, z));
+                        }
+                        d += spacing;
+                    }
+                    dist_to_next = d - seg_length;
+                }
+                if smoothing > 1 && part_samples.len() > 2 {
+                    let half = smoothing / 2;
+                    let n = part_samples.len();
+                    let smoothed = (0..n)
+                        .map(|i| {
+                            let mut window: Vec<f64> = part_samples
+                                [i.saturating_sub(half)..(i + half + 1).min(n)]
+                                .iter()
+                                .map(|s| s.2)
+                                .collect();
+                            window.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                            window[window.len() / 2]
+                        })
+                        .collect::<Vec<f64>>();
+                    for i in 0..n {
+                        part_samples[i].2 = smoothed[i];
+                    }
+                }
+                samples.extend(part_samples);
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / centerline.num_records as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Sampling centerline", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if samples.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No valid DEM elevations were found along the input centerline. Ensure that the centerline overlaps the DEM.",
+            ));
+        }
+        num_points = num_points.min(samples.len());
+
+        const DIMENSIONS: usize = 2;
+        const CAPACITY_PER_NODE: usize = 64;
+        let mut tree = KdTree::with_capacity(DIMENSIONS, CAPACITY_PER_NODE);
+        for (i, s) in samples.iter().enumerate() {
+            tree.add([s.0, s.1], i).unwrap();
+        }
+
+        let samples = Arc::new(samples);
+        let tree = Arc::new(tree);
+        let max_dist_sqrd = if max_dist > 0f64 { max_dist * max_dist } else { f64::INFINITY };
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let dem = dem.clone();
+            let samples = samples.clone();
+            let tree = tree.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let (mut x, mut y): (f64, f64);
+                let mut z: f64;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z = dem.get_value(row, col);
+                        if dem.is_nodata(z) {
+                            continue;
+                        }
+                        x = dem.get_x_from_column(col);
+                        y = dem.get_y_from_row(row);
+                        let ret = tree
+                            .nearest(&[x, y], num_points, &squared_euclidean)
+                            .unwrap();
+                        if ret.is_empty() || ret[0].0 > max_dist_sqrd {
+                            continue;
+                        }
+                        data[col as usize] = if ret[0].0 == 0f64 {
+                            samples[*ret[0].1].2
+                        } else if use_spline && ret.len() > 2 {
+                            let mut centers: Vec<DVector<f64>> = Vec::with_capacity(ret.len());
+                            let mut vals: Vec<DVector<f64>> = Vec::with_capacity(ret.len());
+                            for p in &ret {
+                                let s = samples[*p.1];
+                                // centred on the cell to keep the system well conditioned
+                                centers.push(DVector::from_vec(vec![s.0 - x, s.1 - y]));
+                                vals.push(DVector::from_vec(vec![s.2]));
+                            }
+                            let rbf = RadialBasisFunction::create(
+                                centers,
+                                vals,
+                                Basis::ThinPlateSpine(0.1),
+                                1,
+                            );
+                            rbf.eval(DVector::from_vec(vec![0f64, 0f64]))[0]
+                        } else {
+                            let mut sum_weights = 0f64;
+                            let mut sum = 0f64;
+                            for p in &ret {
+                                let w = 1f64 / p.0.sqrt().powf(weight);
+                                sum_weights += w;
+                                sum += w * samples[*p.1].2;
+                            }
+                            sum / sum_weights
+                        };
+                    }
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut trend = Raster::initialize_using_file(&trend_file, &dem);
+        trend.configs.data_type = DataType::F32;
+        trend.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        for row in 0..rows {
+            let data = rx.recv().expect("Error receiving data from thread.");
+            trend.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Interpolating trend surface", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &dem);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "blue_white_red.plt".to_string();
+        let mut t: f64;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                t = trend.get_value(row, col);
+                if !is_nodata(t, nodata) {
+                    output.set_value(row, col, dem.get_value(row, col) - t);
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let method = if use_spline { "spline" } else { "idw" };
+        let mut outputs = vec![(output, "Relative elevation model")];
+        if !trend_file.is_empty() {
+            outputs.push((trend, "Water-surface trend"));
+        }
+        for (mut output, label) in outputs {
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("{}", label));
+            output.add_metadata_entry(format!("Input DEM file: {}", dem_file));
+            output.add_metadata_entry(format!("Input centerline file: {}", centerline_file));
+            output.add_metadata_entry(format!("Interpolation method: {}", method));
+            output.add_metadata_entry(format!("Num. points: {}", num_points));
+            if !use_spline {
+                output.add_metadata_entry(format!("IDW weight: {}", weight));
+            }
+            output.add_metadata_entry(format!("Sample spacing: {}", spacing));
+            output.add_metadata_entry(format!("Smoothing filter size: {}", smoothing));
+            if max_dist > 0f64 {
+                output.add_metadata_entry(format!("Maximum distance: {}", max_dist));
+            }
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
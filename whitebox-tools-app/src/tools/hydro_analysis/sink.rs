@@ -18,11 +18,11 @@ use std::collections::{BinaryHeap, VecDeque};
 use std::env;
 use std::f64;
 use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool identifies each sink (i.e. topographic depression) in a raster digital elevation model (DEM). A
 /// sink, or depression, is a bowl-like landscape feature, which is characterized by interior drainage. Each
@@ -136,45 +136,12 @@ impl WhiteboxTool for Sink {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut zero_background = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-zero_background" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    zero_background = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let zero_background = parsed.get_bool("zero_background");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -187,17 +154,11 @@ impl WhiteboxTool for Sink {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -219,30 +180,31 @@ impl WhiteboxTool for Sink {
         let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
 
         // Find pit cells. This step is parallelized.
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let filled_dem2 = Arc::new(filled_dem);
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let filled_dem2 = filled_dem2.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut z: f64;
                 let mut zn: f64;
                 let mut flag: bool;
                 let mut pits = vec![];
                 for row in (1..rows - 1).filter(|r| r % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     for col in 1..columns - 1 {
                         z = filled_dem2.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             flag = true;
                             for n in 0..8 {
                                 zn = filled_dem2.get_value(row + dy[n], col + dx[n]);
-                                if zn < z || zn == nodata {
+                                if zn < z || is_nodata(zn, nodata) {
                                     // It either has a lower neighbour or is an edge cell.
                                     flag = false;
                                     break;
@@ -255,7 +217,9 @@ impl WhiteboxTool for Sink {
                         }
                     }
                 }
-                tx.send(pits).unwrap();
+                if tx.send(pits).is_err() {
+                    return;
+                }
             });
         }
 
@@ -264,10 +228,11 @@ impl WhiteboxTool for Sink {
             let mut pits = rx.recv().expect("Error receiving data from thread.");
             undefined_flow_cells.append(&mut pits);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (p + 1) as f64 / num_procs as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding pit cells: {}%", progress);
+                    reporter.progress("Finding pit cells", progress);
                     old_progress = progress;
                 }
             }
@@ -321,14 +286,14 @@ impl WhiteboxTool for Sink {
                             if visited.get_value(rn, cn) == 0 {
                                 zn = filled_dem.get_value(rn, cn);
                                 if !outlet_found {
-                                    if zn >= z && zn != nodata {
+                                    if zn >= z && !is_nodata(zn, nodata) {
                                         minheap.push(GridCell {
                                             row: rn,
                                             column: cn,
                                             priority: zn,
                                         });
                                         visited.set_value(rn, cn, 1);
-                                    } else if zn != nodata {
+                                    } else if !is_nodata(zn, nodata) {
                                         // zn < z
                                         // 'cell' has a lower neighbour that hasn't already passed through minheap.
                                         // Therefore, 'cell' is a pour point cell.
@@ -401,10 +366,11 @@ impl WhiteboxTool for Sink {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * pit_id as f64 / num_deps as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding depressions: {}%", progress);
+                    reporter.progress("Finding depressions", progress);
                     old_progress = progress;
                 }
             }
@@ -444,11 +410,12 @@ impl WhiteboxTool for Sink {
                 }
                 dep_id += 1f64;
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (1.0 - possible_outlets.len() as f64 / num_outlets as f64))
                     as usize;
                 if progress != old_progress {
-                    println!("Labelling depressions: {}%", progress);
+                    reporter.progress("Labelling depressions", progress);
                     old_progress = progress;
                 }
             }
@@ -153,60 +153,13 @@ impl WhiteboxTool for SnapPourPoints {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut pourpts_file = String::new();
-        let mut flow_accum_file = String::new();
-        let mut output_file = String::new();
-        let mut snap_dist = 0.0;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-pour_pts" {
-                pourpts_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-flow_accum" {
-                flow_accum_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-snap_dist" {
-                snap_dist = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut pourpts_file = parsed.get_string("pour_pts").unwrap_or_default();
+        let mut flow_accum_file = parsed.get_string("flow_accum").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let snap_dist = parsed.get_f64("snap_dist").unwrap_or(0.0);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -219,20 +172,12 @@ impl WhiteboxTool for SnapPourPoints {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !pourpts_file.contains(&sep) && !pourpts_file.contains("/") {
-            pourpts_file = format!("{}{}", working_directory, pourpts_file);
-        }
-        if !flow_accum_file.contains(&sep) && !flow_accum_file.contains("/") {
-            flow_accum_file = format!("{}{}", working_directory, flow_accum_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        pourpts_file = resolve_input_path(&pourpts_file, working_directory)?;
+        flow_accum_file = resolve_input_path(&flow_accum_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -282,7 +227,7 @@ impl WhiteboxTool for SnapPourPoints {
             for x in (col - snap_dist_int)..(col + snap_dist_int + 1) {
                 for y in (row - snap_dist_int)..(row + snap_dist_int + 1) {
                     zn = flow_accum.get_value(y, x);
-                    if zn > max_accum && zn != nodata {
+                    if zn > max_accum && !is_nodata(zn, nodata) {
                         max_accum = zn;
                         xn = x;
                         yn = y;
@@ -292,11 +237,12 @@ impl WhiteboxTool for SnapPourPoints {
             x = flow_accum.get_x_from_column(xn);
             y = flow_accum.get_y_from_row(yn);
             output.add_point_record(x, y);
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (pourpts.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
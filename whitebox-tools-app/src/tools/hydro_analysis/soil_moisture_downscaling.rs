@@ -0,0 +1,682 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use crate::na::{DMatrix, DVector};
+use whitebox_raster::*;
+use whitebox_common::rendering::html::*;
+use whitebox_common::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+
+/// This tool downscales a coarse-resolution soil moisture raster (`--input`), such as a SMAP or SMOS
+/// retrieval, to the resolution of a set of fine-resolution topographic covariates (`--covariates`). Typical
+/// covariates include the topographic wetness index (`WetnessIndex`), slope (`Slope`), and elevation. If an
+/// aspect raster (`--aspect`, in degrees) is specified, it is included in the model as its cosine (northness)
+/// and sine (eastness) components, with flat cells (aspect of -1) assigned zero for both. All covariate rasters
+/// must share the same rows, columns, and extent, which define the grid of the output raster (`--output`).
+///
+/// The downscaling uses a regression-then-residual-interpolation (regression kriging-like) approach:
+///
+/// 1. Each covariate is aggregated to the coarse grid as the mean of the fine cells whose centres fall
+///    within each coarse cell. Coarse cells for which fewer than a minimum fraction (`--min_coverage`) of
+///    the fine cells have valid covariate values are excluded from the model.
+/// 2. An ordinary least-squares multiple regression of coarse soil moisture on the aggregated covariates
+///    is fitted.
+/// 3. The regression residuals at the coarse cells are bilinearly interpolated to the centre of each fine
+///    cell. Fine cells for which none of the four surrounding coarse cells has a residual are assigned a
+///    residual of zero, i.e. the regression estimate alone.
+/// 4. The fine-resolution soil moisture estimate is the regression prediction from the fine covariate
+///    values plus the interpolated residual.
+///
+/// Adding the interpolated residuals back ensures that the downscaled surface largely honours the coarse
+/// observations while the fine-scale pattern is imposed by the topographic covariates. The interpolated
+/// residual surface can optionally be output (`--out_residuals`).
+///
+/// The tool outputs a diagnostics report, named after the output raster with an *.html* extension, which
+/// contains the regression coefficients, their standard errors and t-statistics, the model R-squared,
+/// adjusted R-squared, and RMSE, and a mass-conservation check comparing the fine estimates re-aggregated
+/// to the coarse grid against the original coarse values.
+///
+/// # See Also
+/// `WetnessIndex`, `Slope`, `Aspect`, `ImageRegression`, `TrendSurface`
+pub struct SoilMoistureDownscaling {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SoilMoistureDownscaling {
+    pub fn new() -> SoilMoistureDownscaling {
+        // public constructor
+        let name = "SoilMoistureDownscaling".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description = "Downscales coarse soil moisture rasters using fine-resolution topographic covariates.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Coarse Soil Moisture File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input coarse-resolution soil moisture raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Covariate Files".to_owned(),
+            flags: vec!["--covariates".to_owned()],
+            description: "Input fine-resolution covariate raster files (e.g. wetness index, slope).".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Aspect File (optional)".to_owned(),
+            flags: vec!["--aspect".to_owned()],
+            description: "Optional fine-resolution aspect raster file, in degrees.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output fine-resolution soil moisture raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Residuals File (optional)".to_owned(),
+            flags: vec!["--out_residuals".to_owned()],
+            description: "Optional output interpolated residuals raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Coverage Fraction".to_owned(),
+            flags: vec!["--min_coverage".to_owned()],
+            description: "Minimum fraction of valid fine cells needed to include a coarse cell in the model.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=smap.tif --covariates='twi.tif;slope.tif' --aspect=aspect.tif -o=soil_moisture.tif --out_residuals=residuals.tif --min_coverage=0.75", short_exe, name).replace("*", &sep);
+
+        SoilMoistureDownscaling {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SoilMoistureDownscaling {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let covariate_files = parsed.get_string("covariates").unwrap_or_default();
+        let mut aspect_file = parsed.get_string("aspect").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut residuals_file = parsed.get_string("out_residuals").unwrap_or_default();
+        let min_coverage = parsed.get_f64("min_coverage").unwrap_or(0.5f64);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        for file in [&mut input_file, &mut aspect_file, &mut output_file, &mut residuals_file] {
+            *file = resolve_path(file, working_directory);
+        }
+        if min_coverage < 0f64 || min_coverage > 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The minimum coverage fraction (--min_coverage) must be between 0 and 1.",
+            ));
+        }
+
+        let mut cmd = covariate_files.split(";");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = covariate_files.split(",");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let mut covariates: Vec<Raster> = vec![];
+        let mut predictor_names = vec![];
+        for value in vec {
+            if !value.trim().is_empty() {
+                let mut covariate_file = value.trim().to_owned();
+                covariate_file = resolve_input_path(&covariate_file, working_directory)?;
+                let covariate = Raster::new(&covariate_file, "r")?;
+                predictor_names.push(covariate.get_short_filename());
+                covariates.push(covariate);
+            }
+        }
+        if covariates.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one fine-resolution covariate raster (--covariates) must be specified.",
+            ));
+        }
+        let aspect = if !aspect_file.is_empty() {
+            predictor_names.push("cos(aspect)".to_string());
+            predictor_names.push("sin(aspect)".to_string());
+            Some(Raster::new(&aspect_file, "r")?)
+        } else {
+            None
+        };
+
+        let rows = covariates[0].configs.rows as isize;
+        let columns = covariates[0].configs.columns as isize;
+        for r in covariates.iter().chain(aspect.iter()) {
+            if r.configs.rows as isize != rows || r.configs.columns as isize != columns {
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The covariate files must have the same number of rows and columns and spatial extent."));
+            }
+        }
+        let coarse_rows = input.configs.rows as isize;
+        let coarse_columns = input.configs.columns as isize;
+        let coarse_nodata = input.configs.nodata;
+        if input.configs.resolution_x < covariates[0].configs.resolution_x
+            || input.configs.resolution_y < covariates[0].configs.resolution_y
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input soil moisture raster must be of coarser resolution than the covariate rasters.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        // The number of model predictors, excluding the intercept.
+        let k = predictor_names.len();
+        let fine = &covariates[0];
+
+        // Reads the predictor values of a fine grid cell, returning false if any are nodata.
+        let get_predictors = |row: isize, col: isize, x: &mut [f64]| -> bool {
+            for (i, r) in covariates.iter().enumerate() {
+                x[i] = r.get_value(row, col);
+                if is_nodata(x[i], r.configs.nodata) {
+                    return false;
+                }
+            }
+            if let Some(ref a) = aspect {
+                let v = a.get_value(row, col);
+                if is_nodata(v, a.configs.nodata) {
+                    return false;
+                }
+                if v < 0f64 {
+                    // flat
+                    x[k - 2] = 0f64;
+                    x[k - 1] = 0f64;
+                } else {
+                    x[k - 2] = v.to_radians().cos();
+                    x[k - 1] = v.to_radians().sin();
+                }
+            }
+            true
+        };
+
+        // Maps each fine cell to the coarse cell containing its centre.
+        let coarse_row_of = |row: isize| input.get_row_from_y(fine.get_y_from_row(row));
+        let coarse_col_of = |col: isize| input.get_column_from_x(fine.get_x_from_column(col));
+        let coarse_index = |row: isize, col: isize| -> Option<usize> {
+            let (r, c) = (coarse_row_of(row), coarse_col_of(col));
+            if r < 0 || c < 0 || r >= coarse_rows || c >= coarse_columns {
+                return None;
+            }
+            Some((r * coarse_columns + c) as usize)
+        };
+
+        // Aggregate the covariates to the coarse grid.
+        let num_coarse = (coarse_rows * coarse_columns) as usize;
+        let mut sums = vec![0f64; num_coarse * k];
+        let mut num_valid = vec![0usize; num_coarse];
+        let mut num_total = vec![0usize; num_coarse];
+        let mut x = vec![0f64; k];
+        for row in 0..rows {
+            for col in 0..columns {
+                if let Some(idx) = coarse_index(row, col) {
+                    num_total[idx] += 1;
+                    if get_predictors(row, col, &mut x) {
+                        num_valid[idx] += 1;
+                        for i in 0..k {
+                            sums[idx * k + i] += x[i];
+                        }
+                    }
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Aggregating covariates", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut sample_cells = vec![];
+        let mut design = vec![];
+        let mut y = vec![];
+        for idx in 0..num_coarse {
+            let z = input.get_value(idx as isize / coarse_columns, idx as isize % coarse_columns);
+            if is_nodata(z, coarse_nodata) || num_valid[idx] == 0 {
+                continue;
+            }
+            if (num_valid[idx] as f64) < min_coverage * num_total[idx] as f64 {
+                continue;
+            }
+            sample_cells.push(idx);
+            design.push(1f64);
+            for i in 0..k {
+                design.push(sums[idx * k + i] / num_valid[idx] as f64);
+            }
+            y.push(z);
+        }
+
+        let n = y.len();
+        let p = k + 1;
+        if n <= p {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "There are too few valid coarse cells to fit the regression model.",
+            ));
+        }
+
+        if verbose {
+            println!("Fitting regression model...")
+        };
+        let x_matrix = DMatrix::from_row_slice(n, p, &design);
+        let qr = x_matrix.clone().qr();
+        let q = qr.q();
+        let r = qr.r();
+        if !r.is_invertible() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Matrix is not invertible. The covariates may be collinear.",
+            ));
+        }
+        let b = DVector::from_row_slice(&y);
+        let coefficients = (r.try_inverse().unwrap() * q.transpose() * b)
+            .as_slice()
+            .to_vec(); //inv(R).dot(Q.T).dot(y)
+
+        let predict = |x: &[f64]| -> f64 {
+            let mut y_hat = coefficients[0];
+            for i in 0..k {
+                y_hat += coefficients[i + 1] * x[i];
+            }
+            y_hat
+        };
+
+        let mut residuals: Array2D<f64> =
+            Array2D::new(coarse_rows, coarse_columns, f64::NAN, f64::NAN)?;
+        let mut ss_resid = 0f64;
+        let mut sum_y = 0f64;
+        let mut ss_y = 0f64;
+        for (j, &idx) in sample_cells.iter().enumerate() {
+            reporter.check_cancelled()?;
+            let resid = y[j] - predict(&design[j * p + 1..(j + 1) * p]);
+            residuals.set_value(idx as isize / coarse_columns, idx as isize % coarse_columns, resid);
+            ss_resid += resid * resid;
+            sum_y += y[j];
+            ss_y += y[j] * y[j];
+        }
+        let ss_total = ss_y - sum_y * sum_y / n as f64;
+        let r_sqr = if ss_total > 0f64 { 1f64 - ss_resid / ss_total } else { 0f64 };
+        let adj_r_sqr = 1f64 - (1f64 - r_sqr) * (n - 1) as f64 / (n - p) as f64;
+        let rmse = (ss_resid / n as f64).sqrt();
+        let sigma_sqr = ss_resid / (n - p) as f64;
+        let std_errors = match (x_matrix.transpose() * &x_matrix).try_inverse() {
+            Some(inv) => (0..p).map(|i| (sigma_sqr * inv[(i, i)]).sqrt()).collect::<Vec<f64>>(),
+            None => vec![f64::NAN; p],
+        };
+
+        // Apply the model to the fine grid and add the interpolated residuals.
+        let out_nodata = -32768f64;
+        let mut output = Raster::initialize_using_file(&output_file, fine);
+        output.configs.nodata = out_nodata;
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = input.configs.palette.clone();
+        output.reinitialize_values(out_nodata);
+        let mut out_residuals = if !residuals_file.is_empty() {
+            let mut r = Raster::initialize_using_file(&residuals_file, fine);
+            r.configs.nodata = out_nodata;
+            r.configs.data_type = DataType::F32;
+            r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            r.configs.palette = "blue_white_red.plt".to_string();
+            r.reinitialize_values(out_nodata);
+            Some(r)
+        } else {
+            None
+        };
+
+        let coarse_north = input.configs.north;
+        let coarse_west = input.configs.west;
+        let coarse_res_x = input.configs.resolution_x;
+        let coarse_res_y = input.configs.resolution_y;
+        let mut fine_sums = vec![0f64; num_coarse];
+        let mut fine_counts = vec![0usize; num_coarse];
+        let mut num_fine_cells = 0usize;
+        let (mut fr, mut fc): (f64, f64);
+        let (mut r0, mut c0): (isize, isize);
+        let (mut dr, mut dc): (f64, f64);
+        let (mut weight_sum, mut resid_sum, mut v): (f64, f64, f64);
+        for row in 0..rows {
+            for col in 0..columns {
+                if !get_predictors(row, col, &mut x) {
+                    continue;
+                }
+                // Fractional coarse grid position, relative to coarse cell centres.
+                fr = (coarse_north - fine.get_y_from_row(row)) / coarse_res_y - 0.5;
+                fc = (fine.get_x_from_column(col) - coarse_west) / coarse_res_x - 0.5;
+                r0 = fr.floor() as isize;
+                c0 = fc.floor() as isize;
+                dr = fr - r0 as f64;
+                dc = fc - c0 as f64;
+                weight_sum = 0f64;
+                resid_sum = 0f64;
+                for (nr, nc, wt) in [
+                    (r0, c0, (1f64 - dr) * (1f64 - dc)),
+                    (r0, c0 + 1, (1f64 - dr) * dc),
+                    (r0 + 1, c0, dr * (1f64 - dc)),
+                    (r0 + 1, c0 + 1, dr * dc),
+                ] {
+                    v = residuals.get_value(nr, nc);
+                    if !v.is_nan() && wt > 0f64 {
+                        weight_sum += wt;
+                        resid_sum += wt * v;
+                    }
+                }
+                let resid = if weight_sum > 0f64 { resid_sum / weight_sum } else { 0f64 };
+                let z = predict(&x) + resid;
+                output.set_value(row, col, z);
+                if let Some(ref mut r) = out_residuals {
+                    r.set_value(row, col, resid);
+                }
+                num_fine_cells += 1;
+                if let Some(idx) = coarse_index(row, col) {
+                    fine_sums[idx] += z;
+                    fine_counts[idx] += 1;
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Downscaling", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Mass-conservation check: compare the re-aggregated fine estimates to the coarse values.
+        let mut bias = 0f64;
+        let mut mae = 0f64;
+        let mut ss_diff = 0f64;
+        let mut max_diff = 0f64;
+        for (j, &idx) in sample_cells.iter().enumerate() {
+            reporter.check_cancelled()?;
+            if fine_counts[idx] == 0 {
+                continue;
+            }
+            let diff = fine_sums[idx] / fine_counts[idx] as f64 - y[j];
+            bias += diff;
+            mae += diff.abs();
+            ss_diff += diff * diff;
+            if diff.abs() > max_diff {
+                max_diff = diff.abs();
+            }
+        }
+        bias /= n as f64;
+        mae /= n as f64;
+        let rmse_agg = (ss_diff / n as f64).sqrt();
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        // create the output diagnostics report
+        let p_out = path::Path::new(&output_file);
+        let mut extension = String::from(".");
+        let ext = p_out.extension().unwrap().to_str().unwrap();
+        extension.push_str(ext);
+        let output_html_file = output_file.replace(&extension, ".html");
+
+        let f = File::create(output_html_file.clone())?;
+        let mut writer = BufWriter::new(f);
+
+        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+        <html>
+            <head>
+                <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+                <title>Soil Moisture Downscaling Report</title>"#.as_bytes())?;
+
+        // get the style sheet
+        writer.write_all(&get_css().as_bytes())?;
+
+        writer.write_all(
+            &r#"
+            </head>
+            <body>
+                <h1>Soil Moisture Downscaling Report</h1>
+                "#
+            .as_bytes(),
+        )?;
+
+        writer.write_all((format!("<p><strong>Input</strong>: {}</p>", input_file)).as_bytes())?;
+        writer.write_all(
+            (format!("<p><strong>Coarse cells used</strong>: {}</p>", n)).as_bytes(),
+        )?;
+        writer.write_all(
+            (format!("<p><strong>Fine cells estimated</strong>: {}</p>", num_fine_cells)).as_bytes(),
+        )?;
+
+        /////////////////////
+        // Model Fit Table //
+        /////////////////////
+        writer.write_all("<p><table>".as_bytes())?;
+        writer.write_all("<caption>Regression Model Fit</caption>".as_bytes())?;
+        writer.write_all("<tr><th>Statistic</th><th>Value</th></tr>".as_bytes())?;
+        for (label, value) in [
+            ("R-sqr", r_sqr),
+            ("Adj. R-sqr", adj_r_sqr),
+            ("RMSE", rmse),
+        ] {
+            writer.write_all(&format!("<tr><td>{}</td><td class=\"numberCell\">{:.*}</td></tr>", label, 5, value).as_bytes())?;
+        }
+        writer.write_all("</table></p>".as_bytes())?;
+
+        ///////////////////////
+        // Coefficient Table //
+        ///////////////////////
+        writer.write_all("<p><table>".as_bytes())?;
+        writer.write_all("<caption>Regression Coefficients</caption>".as_bytes())?;
+        writer.write_all("<tr><th>Predictor</th><th>Coefficient</th><th>Std. Error</th><th>t</th></tr>".as_bytes())?;
+        for j in 0..p {
+            let label = if j == 0 { "Intercept".to_string() } else { predictor_names[j - 1].clone() };
+            let s = format!(
+                "<td>{}</td><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{:.*}</td><td class=\"numberCell\">{:.*}</td>",
+                label,
+                6,
+                coefficients[j],
+                6,
+                std_errors[j],
+                3,
+                coefficients[j] / std_errors[j]
+            );
+            writer.write_all(&format!("<tr>{}</tr>", s).as_bytes())?;
+        }
+        writer.write_all("</table></p>".as_bytes())?;
+
+        /////////////////////////////
+        // Mass Conservation Table //
+        /////////////////////////////
+        writer.write_all("<p><table>".as_bytes())?;
+        writer.write_all("<caption>Mass Conservation (Aggregated Fine Minus Coarse)</caption>".as_bytes())?;
+        writer.write_all("<tr><th>Statistic</th><th>Value</th></tr>".as_bytes())?;
+        for (label, value) in [
+            ("Mean Difference", bias),
+            ("Mean Absolute Difference", mae),
+            ("RMSE", rmse_agg),
+            ("Max. Absolute Difference", max_diff),
+        ] {
+            writer.write_all(&format!("<tr><td>{}</td><td class=\"numberCell\">{:.*}</td></tr>", label, 6, value).as_bytes())?;
+        }
+        writer.write_all("</table></p>".as_bytes())?;
+
+        writer.write_all("</body>".as_bytes())?;
+
+        let _ = writer.flush();
+
+        if verbose {
+            if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                let output = Command::new("open")
+                    .arg(output_html_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "windows") {
+                let output = Command::new("explorer.exe")
+                    .arg(output_html_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "linux") {
+                let output = Command::new("xdg-open")
+                    .arg(output_html_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            }
+
+            println!("Please see {} for output report.", output_html_file);
+        }
+
+        let mut outputs = vec![(output, "Downscaled soil moisture")];
+        if let Some(r) = out_residuals {
+            outputs.push((r, "Interpolated regression residuals"));
+        }
+        for (mut output, label) in outputs {
+            output.configs.z_units = input.configs.z_units.clone();
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("{}", label));
+            output.add_metadata_entry(format!("Input coarse file: {}", input_file));
+            output.add_metadata_entry(format!("Input covariate files: {}", covariate_files));
+            if !aspect_file.is_empty() {
+                output.add_metadata_entry(format!("Input aspect file: {}", aspect_file));
+            }
+            output.add_metadata_entry(format!("Model R-sqr: {:.5}", r_sqr));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -17,11 +17,11 @@ use std::collections::{BinaryHeap, VecDeque};
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a stochastic analysis of depressions within a DEM, calculating the
 /// probability of each cell belonging to a depression. This land-surface parameter
@@ -199,79 +199,14 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut rmse = 1f64;
-        let mut range = 1f64;
-        let mut iterations = 100;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-rmse" {
-                rmse = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-range" {
-                range = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-iterations" {
-                iterations = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let rmse = parsed.get_f64("rmse").unwrap_or(1f64);
+        let range = parsed.get_f64("range").unwrap_or(1f64);
+        let mut iterations = parsed.get_usize("iterations")?.unwrap_or(100);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -284,17 +219,11 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut reference_cdf: Vec<Vec<f64>> = vec![];
         let mu = 0f64; // assume the mean error is zero
@@ -384,9 +313,10 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
         let multiplier = 1000f64;
         let mut num_nodata = 0usize;
         for row in 0..rows {
+            reporter.check_cancelled()?;
             for col in 0..columns {
                 z = input1.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     input.set_value(row, col, (z * multiplier) as i32);
                 } else {
                     num_nodata += 1;
@@ -403,12 +333,8 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
 
         // let mut error_model: Array2D<i32> = Array2D::new(rows, columns, nodata_i32, nodata_i32).unwrap();
         let background_val = i32::min_value() + 1;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let numcells: f64 = (rows * columns) as f64; // used by the histogram matching
         let dx = [1, 1, 1, 0, -1, -1, -1, 0];
         let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
@@ -425,7 +351,7 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut rng = SmallRng::from_entropy();
                     let mut sn_val: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -436,7 +362,9 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
                                 (sn_val * multiplier * range_in_cells * 2f64) as i32;
                         }
 
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -504,7 +432,7 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
                 for tid in 0..num_procs {
                     let tx = tx.clone();
                     let integral = integral.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         let mut z: i32;
                         let mut sum: i32;
                         let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
@@ -548,7 +476,9 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
                                 }
                             }
 
-                            tx.send((row, data, min_value, max_value)).unwrap();
+                            if tx.send((row, data, min_value, max_value)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -743,10 +673,11 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
             drop(minheap);
             drop(dep_filled);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (iter_num + 1) as f64 / iterations as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -755,6 +686,7 @@ impl WhiteboxTool for StochasticDepressionAnalysis {
         let iters = iterations as f64;
         let mut output = Raster::initialize_using_config(&output_file, &output_config);
         for row in 0..rows {
+            reporter.check_cancelled()?;
             for col in 0..columns {
                 if input.get_value(row, col) != nodata_i32 {
                     output.set_value(row, col, freq_dep.get_value(row, col) as f64 / iters);
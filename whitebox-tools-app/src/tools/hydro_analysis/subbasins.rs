@@ -79,7 +79,7 @@ impl Subbasins {
 
         parameters.push(ToolParameter {
             name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -144,54 +144,13 @@ impl WhiteboxTool for Subbasins {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut d8_file = String::new();
-        let mut streams_file = String::new();
-        let mut output_file = String::new();
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-d8_pntr" || vec[0].to_lowercase() == "--d8_pntr" {
-                if keyval {
-                    d8_file = vec[1].to_string();
-                } else {
-                    d8_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-streams" || vec[0].to_lowercase() == "--streams" {
-                if keyval {
-                    streams_file = vec[1].to_string();
-                } else {
-                    streams_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-esri_pntr"
-                || vec[0].to_lowercase() == "--esri_pntr"
-                || vec[0].to_lowercase() == "--esri_style"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("d8_pntr").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let esri_style = parsed.get_bool("esri_pntr");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -204,20 +163,12 @@ impl WhiteboxTool for Subbasins {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !d8_file.contains(&sep) && !d8_file.contains("/") {
-            d8_file = format!("{}{}", working_directory, d8_file);
-        }
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        d8_file = resolve_input_path(&d8_file, working_directory)?;
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -238,11 +189,9 @@ impl WhiteboxTool for Subbasins {
         if streams.configs.rows != pntr.configs.rows
             || streams.configs.columns != pntr.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&streams, &pntr, verbose);
 
         // First assign each stream link a unique identifier
         let mut pourpts: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
@@ -278,7 +227,7 @@ impl WhiteboxTool for Subbasins {
                         current_id += 1f64;
                     }
                 } else {
-                    if pntr[(row, col)] != pntr_nodata {
+                    if !is_nodata(pntr[(row, col)], pntr_nodata) {
                         pourpts[(row, col)] = 0.0;
                     } else {
                         pourpts[(row, col)] = nodata;
@@ -286,10 +235,11 @@ impl WhiteboxTool for Subbasins {
                     num_solved_cells += 1;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Processing streams: {}%", progress);
+                    reporter.progress("Processing streams", progress);
                     old_progress = progress;
                 }
             }
@@ -349,7 +299,7 @@ impl WhiteboxTool for Subbasins {
                 if num_inflowing[(row_n, col_n)] > 1 {
                     current_id += 1f64;
                     pourpts[(row_n, col_n)] = current_id;
-                } else if pourpts[(row_n, col_n)] == nodata {
+                } else if is_nodata(pourpts[(row_n, col_n)], nodata) {
                     pourpts[(row_n, col_n)] = val;
                 }
 
@@ -359,10 +309,11 @@ impl WhiteboxTool for Subbasins {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Processing streams: {}%", progress);
+                    reporter.progress("Processing streams", progress);
                     old_progress = progress;
                 }
             }
@@ -379,18 +330,19 @@ impl WhiteboxTool for Subbasins {
         let mut z: f64;
         for row in 0..rows {
             for col in 0..columns {
-                if pntr[(row, col)] == pntr_nodata {
+                if is_nodata(pntr[(row, col)], pntr_nodata) {
                     output[(row, col)] = nodata;
                 }
                 z = pourpts[(row, col)];
-                if z != nodata && z > 0.0 {
+                if !is_nodata(z, nodata) && z > 0.0 {
                     output[(row, col)] = z;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Watershedding (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Watershedding (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -447,10 +399,11 @@ impl WhiteboxTool for Subbasins {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Watershedding (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Watershedding (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,1075 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::point_in_poly;
+use whitebox_raster::*;
+use whitebox_common::structures::{Array2D, Point2D};
+use crate::tools::*;
+use whitebox_vector::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool creates a hydrologically correct digital elevation model (DEM) from topographic vector data,
+/// in a manner similar to the ANUDEM method of Hutchinson (1989), which underlies the *Topo to Raster*
+/// tool found in some GIS. It is intended for areas where lidar or other high-resolution elevation data
+/// are unavailable and the best sources of topographic information are the contour lines, spot heights,
+/// and hydrography layers of topographic maps.
+///
+/// The tool accepts the following inputs, each of which is optional, although at least one of the contour
+/// and spot height inputs must be specified:
+///
+/// - Contour lines (`--contours`), with elevations contained in an attribute field (`--contour_field`);
+/// - Spot heights (`--points`), with elevations contained in an attribute field (`--point_field`);
+/// - Stream lines (`--streams`), used for drainage enforcement;
+/// - Lake polygons (`--lakes`), which are flattened to a constant water level; and
+/// - Known sinks (`--sinks`), points marking genuine closed depressions (e.g. karst sinkholes) that
+///   should not be removed by drainage enforcement.
+///
+/// If the specified elevation field does not exist and the vector file contains z-values (e.g. a
+/// PolyLineZ contour file), the z-values are used instead.
+///
+/// The surface is interpolated using an iterative finite-difference solution of a roughness-minimizing
+/// surface, in which the contour and spot height cells are held fixed. The roughness penalty is a blend of
+/// a first-derivative (membrane) penalty, which produces surfaces that do not overshoot the data, and a
+/// second-derivative (thin plate) penalty, which produces smoother surfaces but may overshoot; the
+/// `--smoothing` parameter (0-1) sets the weight of the thin plate penalty. The solution is found using a
+/// coarse-to-fine (multi-grid) scheme, in which the surface calculated on a coarse grid is used as the
+/// starting point at the next finer grid, with up to `--max_iterations` Gauss-Seidel iterations at each
+/// level, or until the largest change in elevation during an iteration is less than `--tolerance`.
+///
+/// Drainage enforcement then proceeds in three steps. First, lakes are assigned a level equal to the lowest
+/// interpolated elevation along their shoreline. Second, each stream line is traced from its higher end to
+/// its lower end and the elevations of the stream cells are constrained to descend strictly in the
+/// downstream direction; stream lines therefore do not need to be digitized in the flow direction,
+/// although the network should be topologically connected. The surface is then re-interpolated with the
+/// lake and stream cells held fixed, which carves valleys along the streams. Third, spurious sinks, i.e.
+/// depressions in the surface that are not supported by the data, are removed using a priority-flood
+/// operation that drains each cell to the grid edge, a lake, or a known sink, with a small elevation
+/// increment to ensure that there are no flats. The output DEM is therefore free of depressions
+/// except at lakes and known sinks.
+///
+/// The output grid resolution is set using `--cell_size`, in which case the extent is that of the input
+/// vectors, or alternatively, the resolution and extent may be taken from an existing base raster (`--base`).
+///
+/// # Reference
+/// Hutchinson, M. F. (1989). A new procedure for gridding elevation and stream line data with automatic
+/// removal of spurious pits. *Journal of Hydrology*, 106(3-4), 211-232.
+///
+/// # See Also
+/// `TINGridding`, `NaturalNeighbourInterpolation`, `FillBurn`, `FillDepressions`, `BurnStreamsAtRoads`
+pub struct TopoToRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TopoToRaster {
+    pub fn new() -> TopoToRaster {
+        // public constructor
+        let name = "TopoToRaster".to_string();
+        let toolbox = "Hydrological Analysis".to_string();
+        let description = "Interpolates a hydrologically correct DEM from contours, spot heights, streams, and lakes.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Contour Lines File (optional)".to_owned(),
+            flags: vec!["--contours".to_owned()],
+            description: "Input vector contour lines file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Contour Elevation Field".to_owned(),
+            flags: vec!["--contour_field".to_owned()],
+            description: "Name of the contour elevation attribute field.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--contours".to_string(),
+            ),
+            default_value: Some("ELEV".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Spot Heights File (optional)".to_owned(),
+            flags: vec!["--points".to_owned()],
+            description: "Input vector spot heights (points) file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Spot Height Elevation Field".to_owned(),
+            flags: vec!["--point_field".to_owned()],
+            description: "Name of the spot height elevation attribute field.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--points".to_string(),
+            ),
+            default_value: Some("ELEV".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Streams File (optional)".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input vector stream lines file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Lakes File (optional)".to_owned(),
+            flags: vec!["--lakes".to_owned()],
+            description: "Input vector lake polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Known Sinks File (optional)".to_owned(),
+            flags: vec!["--sinks".to_owned()],
+            description: "Input vector points file of known sinks.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster DEM file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Cell Size (optional)".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Optionally specified cell size of output raster. Not used when base raster is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Base Raster File (optional)".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optionally specified input base raster file. Not used when a cell size is specified.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Smoothing (0-1)".to_owned(),
+            flags: vec!["--smoothing".to_owned()],
+            description: "Weight of the thin plate (second-derivative) roughness penalty, from 0 to 1.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Iterations".to_owned(),
+            flags: vec!["--max_iterations".to_owned()],
+            description: "Maximum number of iterations at each grid level.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("500".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Convergence Tolerance".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Iteration stops when the largest elevation change is less than this value.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.001".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --contours=contours.shp --contour_field=ELEV --points=spot_heights.shp --streams=streams.shp --lakes=lakes.shp -o=dem.tif --cell_size=10.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        TopoToRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TopoToRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut contours_file = parsed.get_string("contours").unwrap_or_default();
+        let contour_field = parsed.get_string("contour_field").unwrap_or(String::from("ELEV"));
+        let mut points_file = parsed.get_string("points").unwrap_or_default();
+        let point_field = parsed.get_string("point_field").unwrap_or(String::from("ELEV"));
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut lakes_file = parsed.get_string("lakes").unwrap_or_default();
+        let mut sinks_file = parsed.get_string("sinks").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let grid_res = parsed.get_f64("cell_size").unwrap_or(0f64);
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+        let mut smoothing = parsed.get_f64("smoothing").unwrap_or(0.5f64);
+        let mut max_iterations = parsed.get_usize("max_iterations")?.unwrap_or(500usize);
+        let tolerance = parsed.get_f64("tolerance").unwrap_or(0.001f64);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        for file in [
+            &mut contours_file,
+            &mut points_file,
+            &mut streams_file,
+            &mut lakes_file,
+            &mut sinks_file,
+            &mut output_file,
+            &mut base_file,
+        ] {
+            *file = resolve_path(file, working_directory);
+        }
+
+        if contours_file.is_empty() && points_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one of the contours (--contours) and spot heights (--points) inputs must be specified.",
+            ));
+        }
+        smoothing = smoothing.max(0f64).min(1f64);
+        if max_iterations < 1 {
+            max_iterations = 1;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let contours = read_vector(&contours_file, ShapeType::PolyLine, "contours")?;
+        let points = read_vector(&points_file, ShapeType::Point, "spot heights")?;
+        let streams = read_vector(&streams_file, ShapeType::PolyLine, "streams")?;
+        let lakes = read_vector(&lakes_file, ShapeType::Polygon, "lakes")?;
+        let sinks = read_vector(&sinks_file, ShapeType::Point, "known sinks")?;
+
+        let start = Instant::now();
+
+        let nodata = -32768.0f64;
+        let mut output = if !base_file.is_empty() && grid_res == 0f64 {
+            let mut base = Raster::new(&base_file, "r")?;
+            base.configs.nodata = nodata;
+            Raster::initialize_using_file(&output_file, &base)
+        } else {
+            if grid_res <= 0f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The specified grid resolution is incorrect. Either a non-zero grid resolution \nor an input existing base file name must be used.",
+                ));
+            }
+            // base the output raster on the grid_res and the extent of the input vectors.
+            let (mut x_min, mut x_max, mut y_min, mut y_max) =
+                (f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY);
+            let mut projection = String::new();
+            for shp in [&contours, &points, &streams, &lakes] {
+                reporter.check_cancelled()?;
+                if let Some(shp) = shp {
+                    x_min = x_min.min(shp.header.x_min);
+                    x_max = x_max.max(shp.header.x_max);
+                    y_min = y_min.min(shp.header.y_min);
+                    y_max = y_max.max(shp.header.y_max);
+                    if projection.is_empty() {
+                        projection = shp.projection.clone();
+                    }
+                }
+            }
+            let west: f64 = x_min;
+            let north: f64 = y_max;
+            let rows: isize = (((north - y_min) / grid_res).ceil() as isize).max(1);
+            let columns: isize = (((x_max - west) / grid_res).ceil() as isize).max(1);
+            let south: f64 = north - rows as f64 * grid_res;
+            let east = west + columns as f64 * grid_res;
+
+            let mut configs = RasterConfigs {
+                ..Default::default()
+            };
+            configs.rows = rows as usize;
+            configs.columns = columns as usize;
+            configs.north = north;
+            configs.south = south;
+            configs.east = east;
+            configs.west = west;
+            configs.resolution_x = grid_res;
+            configs.resolution_y = grid_res;
+            configs.nodata = nodata;
+            configs.data_type = DataType::F32;
+            configs.photometric_interp = PhotometricInterpretation::Continuous;
+            configs.set_crs(&whitebox_common::spatial_ref_system::CoordinateReferenceSystem::from_wkt(&projection));
+
+            Raster::initialize_using_config(&output_file, &configs)
+        };
+        output.configs.nodata = nodata; // in case a base image is used with a different nodata value.
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "spectrum.pal".to_string();
+
+        let rows = output.configs.rows as isize;
+        let columns = output.configs.columns as isize;
+        let step = output.configs.resolution_x.min(output.configs.resolution_y) / 2f64;
+
+        // Rasterize the elevation data. Cells containing more than one
+        // sample are assigned the mean of the samples.
+        if verbose {
+            println!("Rasterizing elevation data...");
+        }
+        let mut fixed_sum: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+        let mut fixed_n: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+        if let Some(shp) = &contours {
+            let use_z = elevation_source(shp, &contour_field, "contours")?;
+            for record_num in 0..shp.num_records {
+                reporter.check_cancelled()?;
+                let record = shp.get_record(record_num);
+                let z_values = feature_elevations(shp, record_num, &contour_field, use_z);
+                for (start, end) in part_ranges(record) {
+                    for (row, col, z) in trace_line(
+                        &output,
+                        &record.points[start..end],
+                        &z_values[start..end],
+                        step,
+                    ) {
+                        fixed_sum.increment(row, col, z);
+                        fixed_n.increment(row, col, 1f64);
+                    }
+                }
+            }
+        }
+        if let Some(shp) = &points {
+            let use_z = elevation_source(shp, &point_field, "spot heights")?;
+            for record_num in 0..shp.num_records {
+                reporter.check_cancelled()?;
+                let record = shp.get_record(record_num);
+                let z_values = feature_elevations(shp, record_num, &point_field, use_z);
+                for i in 0..record.num_points as usize {
+                    let row = output.get_row_from_y(record.points[i].y);
+                    let col = output.get_column_from_x(record.points[i].x);
+                    if row >= 0 && row < rows && col >= 0 && col < columns {
+                        fixed_sum.increment(row, col, z_values[i]);
+                        fixed_n.increment(row, col, 1f64);
+                    }
+                }
+            }
+        }
+        let mut fixed: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut num_fixed = 0usize;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                if fixed_n.get_value(row, col) > 0f64 {
+                    fixed.set_value(row, col, fixed_sum.get_value(row, col) / fixed_n.get_value(row, col));
+                    num_fixed += 1;
+                }
+            }
+        }
+        drop(fixed_sum);
+        drop(fixed_n);
+        if num_fixed == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "None of the input elevation data fall within the output grid.",
+            ));
+        }
+
+        // Interpolate a preliminary surface, coarse-to-fine.
+        if verbose {
+            println!("Interpolating the surface...");
+        }
+        let mut num_levels = 1usize;
+        while (rows.max(columns) >> num_levels) >= 16 {
+            reporter.check_cancelled()?;
+            num_levels += 1;
+        }
+        let mut surface: Array2D<f64> = Array2D::new(1, 1, nodata, nodata)?;
+        for level in (0..num_levels).rev() {
+            let factor = 1isize << level;
+            let level_fixed = if level == 0 {
+                fixed.duplicate()
+            } else {
+                coarsen(&fixed, factor)?
+            };
+            surface = if level == num_levels - 1 {
+                let mut mean = 0f64;
+                let mut n = 0f64;
+                for row in 0..level_fixed.rows() {
+                    for col in 0..level_fixed.columns() {
+                        let z = level_fixed.get_value(row, col);
+                        if !is_nodata(z, nodata) {
+                            mean += z;
+                            n += 1f64;
+                        }
+                    }
+                }
+                Array2D::new(level_fixed.rows(), level_fixed.columns(), mean / n, nodata)?
+            } else {
+                refine(&surface, level_fixed.rows(), level_fixed.columns())?
+            };
+            let iterations = solve(&mut surface, &level_fixed, smoothing, max_iterations, tolerance);
+            if verbose {
+                println!(
+                    "Grid level {} of {} ({}x{}): {} iterations",
+                    num_levels - level,
+                    num_levels,
+                    level_fixed.rows(),
+                    level_fixed.columns(),
+                    iterations
+                );
+            }
+        }
+
+        // Drainage enforcement
+        let mut lake_cells: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let mut num_lakes = 0usize;
+        if let Some(shp) = &lakes {
+            if verbose {
+                println!("Flattening lakes...");
+            }
+            for record_num in 0..shp.num_records {
+                reporter.check_cancelled()?;
+                let record = shp.get_record(record_num);
+                let cells = polygon_cells(&output, record);
+                if cells.is_empty() {
+                    continue;
+                }
+                for &(row, col) in &cells {
+                    lake_cells.set_value(row, col, 1u8);
+                }
+                // the lake level is the lowest elevation along the shoreline
+                let mut level = f64::INFINITY;
+                for &(row, col) in &cells {
+                    for (dr, dc) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                        let (rn, cn) = (row + dr, col + dc);
+                        if rn >= 0 && rn < rows && cn >= 0 && cn < columns && lake_cells.get_value(rn, cn) == 0 {
+                            level = level.min(surface.get_value(rn, cn));
+                        }
+                    }
+                }
+                if !level.is_finite() {
+                    // the lake covers the grid
+                    level = cells.iter().map(|&(r, c)| surface.get_value(r, c)).fold(f64::INFINITY, f64::min);
+                }
+                for &(row, col) in &cells {
+                    fixed.set_value(row, col, level);
+                }
+                num_lakes += 1;
+            }
+        }
+
+        let elev_digits = (fixed_max(&fixed, nodata).abs() as i64).to_string().len();
+        let small_num = 1f64 / 10f64.powi((9 - elev_digits as i32).max(1));
+
+        let mut num_stream_cells = 0usize;
+        if let Some(shp) = &streams {
+            if verbose {
+                println!("Enforcing stream descent...");
+            }
+            // trace the stream lines, each ordered from its higher to its lower end
+            let mut stream_paths: Vec<Vec<(isize, isize)>> = vec![];
+            for record_num in 0..shp.num_records {
+                reporter.check_cancelled()?;
+                let record = shp.get_record(record_num);
+                let z_values = vec![0f64; record.num_points as usize];
+                for (start, end) in part_ranges(record) {
+                    let mut path: Vec<(isize, isize)> = trace_line(
+                        &output,
+                        &record.points[start..end],
+                        &z_values[start..end],
+                        step,
+                    )
+                    .iter()
+                    .map(|&(row, col, _)| (row, col))
+                    .collect();
+                    if path.len() < 2 {
+                        continue;
+                    }
+                    let (r1, c1) = path[0];
+                    let (r2, c2) = path[path.len() - 1];
+                    if surface.get_value(r1, c1) < surface.get_value(r2, c2) {
+                        path.reverse();
+                    }
+                    stream_paths.push(path);
+                }
+            }
+
+            let mut stream_z: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+            for path in &stream_paths {
+                for &(row, col) in path {
+                    let z = if !is_nodata(fixed.get_value(row, col), nodata) {
+                        fixed.get_value(row, col)
+                    } else {
+                        surface.get_value(row, col)
+                    };
+                    stream_z.set_value(row, col, z);
+                }
+            }
+            // Descent is enforced along each path, with the values at confluences shared
+            // between paths, until no further changes occur.
+            let mut changed = true;
+            let mut num_passes = 0;
+            while changed && num_passes <= stream_paths.len() {
+                reporter.check_cancelled()?;
+                changed = false;
+                for path in &stream_paths {
+                    let mut prev = f64::INFINITY;
+                    for &(row, col) in path {
+                        if lake_cells.get_value(row, col) == 1 {
+                            // streams leave a lake at its level
+                            prev = fixed.get_value(row, col);
+                            continue;
+                        }
+                        let z = stream_z.get_value(row, col);
+                        if z > prev - small_num {
+                            stream_z.set_value(row, col, prev - small_num);
+                            changed = true;
+                        }
+                        prev = stream_z.get_value(row, col);
+                    }
+                }
+                num_passes += 1;
+            }
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    let z = stream_z.get_value(row, col);
+                    if !is_nodata(z, nodata) && lake_cells.get_value(row, col) == 0 {
+                        fixed.set_value(row, col, z);
+                        num_stream_cells += 1;
+                    }
+                }
+            }
+        }
+
+        if num_lakes > 0 || num_stream_cells > 0 {
+            if verbose {
+                println!("Re-interpolating the surface with drainage constraints...");
+            }
+            let iterations = solve(&mut surface, &fixed, smoothing, max_iterations, tolerance);
+            if verbose {
+                println!("{} iterations", iterations);
+            }
+        }
+
+        // Remove spurious sinks using a priority-flood operation seeded at the grid
+        // edges, the lakes, and the known sinks.
+        if verbose {
+            println!("Removing spurious sinks...");
+        }
+        let mut visited: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let mut minheap = BinaryHeap::new();
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                if row == 0 || row == rows - 1 || col == 0 || col == columns - 1 || lake_cells.get_value(row, col) == 1 {
+                    visited.set_value(row, col, 1u8);
+                    minheap.push(GridCell {
+                        row: row,
+                        column: col,
+                        priority: surface.get_value(row, col),
+                    });
+                }
+            }
+        }
+        if let Some(shp) = &sinks {
+            for record_num in 0..shp.num_records {
+                reporter.check_cancelled()?;
+                let record = shp.get_record(record_num);
+                for p in &record.points {
+                    let row = output.get_row_from_y(p.y);
+                    let col = output.get_column_from_x(p.x);
+                    if row >= 0 && row < rows && col >= 0 && col < columns && visited.get_value(row, col) == 0 {
+                        visited.set_value(row, col, 1u8);
+                        minheap.push(GridCell {
+                            row: row,
+                            column: col,
+                            priority: surface.get_value(row, col),
+                        });
+                    }
+                }
+            }
+        }
+        let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+        let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let num_cells = (rows * columns) as f64;
+        let mut num_solved = 0usize;
+        let mut num_raised = 0usize;
+        while let Some(cell) = minheap.pop() {
+            let z = surface.get_value(cell.row, cell.column);
+            for n in 0..8 {
+                let (rn, cn) = (cell.row + d_y[n], cell.column + d_x[n]);
+                if rn < 0 || rn >= rows || cn < 0 || cn >= columns || visited.get_value(rn, cn) == 1 {
+                    continue;
+                }
+                visited.set_value(rn, cn, 1u8);
+                let mut zn = surface.get_value(rn, cn);
+                if zn < z + small_num {
+                    zn = z + small_num;
+                    surface.set_value(rn, cn, zn);
+                    num_raised += 1;
+                }
+                minheap.push(GridCell {
+                    row: rn,
+                    column: cn,
+                    priority: zn,
+                });
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                num_solved += 1;
+                progress = (100.0_f64 * num_solved as f64 / num_cells) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        for row in 0..rows {
+            output.set_row_data(row, surface.get_row_data(row));
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        if !contours_file.is_empty() {
+            output.add_metadata_entry(format!("Contours file: {}", contours_file));
+        }
+        if !points_file.is_empty() {
+            output.add_metadata_entry(format!("Spot heights file: {}", points_file));
+        }
+        if !streams_file.is_empty() {
+            output.add_metadata_entry(format!("Streams file: {}", streams_file));
+        }
+        if !lakes_file.is_empty() {
+            output.add_metadata_entry(format!("Lakes file: {}", lakes_file));
+        }
+        if !sinks_file.is_empty() {
+            output.add_metadata_entry(format!("Known sinks file: {}", sinks_file));
+        }
+        output.add_metadata_entry(format!("Smoothing: {}", smoothing));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Number of data cells: {}", num_fixed);
+            println!("Number of lakes: {}", num_lakes);
+            println!("Number of stream cells: {}", num_stream_cells);
+            println!("Number of cells raised to remove sinks: {}", num_raised);
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads an optional vector input, checking its base shape type.
+fn read_vector(file_name: &str, shape_type: ShapeType, label: &str) -> Result<Option<Shapefile>, Error> {
+    if file_name.is_empty() {
+        return Ok(None);
+    }
+    let shp = Shapefile::read(file_name)?;
+    let base_type = shp.header.shape_type.base_shape_type();
+    let ok = if shape_type == ShapeType::Point {
+        base_type == ShapeType::Point || base_type == ShapeType::MultiPoint
+    } else {
+        base_type == shape_type
+    };
+    if !ok {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("The {} input must be of {:?} base shape type.", label, shape_type),
+        ));
+    }
+    Ok(Some(shp))
+}
+
+/// Determines whether the elevations of a vector are read from an attribute field (false)
+/// or from the z-values of the geometries (true).
+fn elevation_source(shp: &Shapefile, field_name: &str, label: &str) -> Result<bool, Error> {
+    match shp.attributes.get_field_num(field_name) {
+        Some(i) => {
+            if !shp.attributes.is_field_numeric(i) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The elevation field of the {} input is non-numeric.", label),
+                ));
+            }
+            Ok(false)
+        }
+        None => {
+            if shp.header.shape_type.dimension() == ShapeTypeDimension::Z {
+                Ok(true)
+            } else {
+                Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The field '{}' does not exist in the {} input, which does not contain z-values.",
+                        field_name, label
+                    ),
+                ))
+            }
+        }
+    }
+}
+
+/// Returns the elevations of each vertex of a record.
+fn feature_elevations(shp: &Shapefile, record_num: usize, field_name: &str, use_z: bool) -> Vec<f64> {
+    let record = shp.get_record(record_num);
+    if use_z {
+        return record.z_array.clone();
+    }
+    let z = match shp.attributes.get_value(record_num, field_name) {
+        FieldData::Int(val) => val as f64,
+        FieldData::Real(val) => val,
+        _ => f64::NAN, // likely a null field
+    };
+    vec![z; record.num_points as usize]
+}
+
+/// Returns the start and end vertex indices of each part of a record.
+fn part_ranges(record: &ShapefileGeometry) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    for part in 0..record.num_parts as usize {
+        let part_start = record.parts[part] as usize;
+        let part_end = if part < record.num_parts as usize - 1 {
+            record.parts[part + 1] as usize
+        } else {
+            record.num_points as usize
+        };
+        ranges.push((part_start, part_end));
+    }
+    ranges
+}
+
+/// Traces a line through the grid, returning the ordered sequence of cells that it crosses,
+/// within the grid, and the elevation interpolated along the line at each cell.
+fn trace_line(grid: &Raster, points: &[Point2D], z_values: &[f64], step: f64) -> Vec<(isize, isize, f64)> {
+    let rows = grid.configs.rows as isize;
+    let columns = grid.configs.columns as isize;
+    let mut cells: Vec<(isize, isize, f64)> = vec![];
+    let mut push = |x: f64, y: f64, z: f64| {
+        let row = grid.get_row_from_y(y);
+        let col = grid.get_column_from_x(x);
+        if row < 0 || row >= rows || col < 0 || col >= columns || z.is_nan() {
+            return;
+        }
+        if let Some(&(r, c, _)) = cells.last() {
+            if r == row && c == col {
+                return;
+            }
+        }
+        cells.push((row, col, z));
+    };
+    if points.len() == 1 {
+        push(points[0].x, points[0].y, z_values[0]);
+    }
+    for i in 1..points.len() {
+        let (p1, p2) = (points[i - 1], points[i]);
+        let length = p1.distance(&p2);
+        let num_steps = (length / step).ceil().max(1f64) as usize;
+        for s in 0..=num_steps {
+            let t = s as f64 / num_steps as f64;
+            push(
+                p1.x + t * (p2.x - p1.x),
+                p1.y + t * (p2.y - p1.y),
+                z_values[i - 1] + t * (z_values[i] - z_values[i - 1]),
+            );
+        }
+    }
+    cells
+}
+
+/// Returns the cells of the grid whose centres are inside a polygon. Holes are handled
+/// by counting the number of parts that contain each cell centre.
+fn polygon_cells(grid: &Raster, record: &ShapefileGeometry) -> Vec<(isize, isize)> {
+    let rows = grid.configs.rows as isize;
+    let columns = grid.configs.columns as isize;
+    let top = grid.get_row_from_y(record.y_max).max(0);
+    let bottom = grid.get_row_from_y(record.y_min).min(rows - 1);
+    let left = grid.get_column_from_x(record.x_min).max(0);
+    let right = grid.get_column_from_x(record.x_max).min(columns - 1);
+    let parts = part_ranges(record);
+    let mut cells = vec![];
+    for row in top..=bottom {
+        let y = grid.get_y_from_row(row);
+        for col in left..=right {
+            let p = Point2D::new(grid.get_x_from_column(col), y);
+            let mut count = 0;
+            for &(start, end) in &parts {
+                if point_in_poly(&p, &record.points[start..end]) {
+                    count += 1;
+                }
+            }
+            if count % 2 == 1 {
+                cells.push((row, col));
+            }
+        }
+    }
+    cells
+}
+
+fn fixed_max(fixed: &Array2D<f64>, nodata: f64) -> f64 {
+    let mut max = 0f64;
+    for row in 0..fixed.rows() {
+        for col in 0..fixed.columns() {
+            let z = fixed.get_value(row, col);
+            if !is_nodata(z, nodata) && z.abs() > max {
+                max = z.abs();
+            }
+        }
+    }
+    max
+}
+
+/// Aggregates the fixed (data) cells to a coarser grid by averaging the data cells
+/// within each block of `factor` x `factor` cells.
+fn coarsen(fixed: &Array2D<f64>, factor: isize) -> Result<Array2D<f64>, Error> {
+    let nodata = fixed.nodata();
+    let rows = (fixed.rows() + factor - 1) / factor;
+    let columns = (fixed.columns() + factor - 1) / factor;
+    let mut sum: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+    let mut n: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+    for row in 0..fixed.rows() {
+        for col in 0..fixed.columns() {
+            let z = fixed.get_value(row, col);
+            if !is_nodata(z, nodata) {
+                sum.increment(row / factor, col / factor, z);
+                n.increment(row / factor, col / factor, 1f64);
+            }
+        }
+    }
+    let mut coarse: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    for row in 0..rows {
+        for col in 0..columns {
+            if n.get_value(row, col) > 0f64 {
+                coarse.set_value(row, col, sum.get_value(row, col) / n.get_value(row, col));
+            }
+        }
+    }
+    Ok(coarse)
+}
+
+/// Bilinearly resamples a coarse surface to a grid with twice the resolution (approximately)
+/// and the given dimensions.
+fn refine(coarse: &Array2D<f64>, rows: isize, columns: isize) -> Result<Array2D<f64>, Error> {
+    let mut fine: Array2D<f64> = Array2D::new(rows, columns, 0f64, coarse.nodata())?;
+    let max_row = (coarse.rows() - 1) as f64;
+    let max_col = (coarse.columns() - 1) as f64;
+    for row in 0..rows {
+        let y = ((row as f64 + 0.5) / 2f64 - 0.5).max(0f64).min(max_row);
+        let r0 = y.floor() as isize;
+        let r1 = (r0 + 1).min(coarse.rows() - 1);
+        let ty = y - r0 as f64;
+        for col in 0..columns {
+            let x = ((col as f64 + 0.5) / 2f64 - 0.5).max(0f64).min(max_col);
+            let c0 = x.floor() as isize;
+            let c1 = (c0 + 1).min(coarse.columns() - 1);
+            let tx = x - c0 as f64;
+            let top = coarse.get_value(r0, c0) * (1f64 - tx) + coarse.get_value(r0, c1) * tx;
+            let bottom = coarse.get_value(r1, c0) * (1f64 - tx) + coarse.get_value(r1, c1) * tx;
+            fine.set_value(row, col, top * (1f64 - ty) + bottom * ty);
+        }
+    }
+    Ok(fine)
+}
+
+/// Iteratively solves for the surface that minimizes the blended membrane and thin plate
+/// roughness penalty, with the data cells held fixed, using Gauss-Seidel iteration. Returns
+/// the number of iterations performed.
+fn solve(
+    surface: &mut Array2D<f64>,
+    fixed: &Array2D<f64>,
+    smoothing: f64,
+    max_iterations: usize,
+    tolerance: f64,
+) -> usize {
+    let rows = surface.rows();
+    let columns = surface.columns();
+    let nodata = fixed.nodata();
+    for row in 0..rows {
+        for col in 0..columns {
+            let z = fixed.get_value(row, col);
+            if !is_nodata(z, nodata) {
+                surface.set_value(row, col, z);
+            }
+        }
+    }
+    // values beyond the grid edge are reflected, i.e. a zero-gradient boundary
+    let value = |s: &Array2D<f64>, r: isize, c: isize| -> f64 {
+        let r = if r < 0 { -r } else if r >= rows { 2 * rows - 2 - r } else { r };
+        let c = if c < 0 { -c } else if c >= columns { 2 * columns - 2 - c } else { c };
+        s.get_value(r.max(0).min(rows - 1), c.max(0).min(columns - 1))
+    };
+    let mut iterations = 0;
+    while iterations < max_iterations {
+        let mut max_change = 0f64;
+        for row in 0..rows {
+            for col in 0..columns {
+                if !is_nodata(fixed.get_value(row, col), nodata) {
+                    continue;
+                }
+                let sum4 = value(surface, row - 1, col)
+                    + value(surface, row + 1, col)
+                    + value(surface, row, col - 1)
+                    + value(surface, row, col + 1);
+                let membrane = sum4 / 4f64;
+                let z = if smoothing > 0f64 && rows > 4 && columns > 4 {
+                    let sum_diag = value(surface, row - 1, col - 1)
+                        + value(surface, row - 1, col + 1)
+                        + value(surface, row + 1, col - 1)
+                        + value(surface, row + 1, col + 1);
+                    let sum_far = value(surface, row - 2, col)
+                        + value(surface, row + 2, col)
+                        + value(surface, row, col - 2)
+                        + value(surface, row, col + 2);
+                    let thin_plate = (8f64 * sum4 - 2f64 * sum_diag - sum_far) / 20f64;
+                    (1f64 - smoothing) * membrane + smoothing * thin_plate
+                } else {
+                    membrane
+                };
+                let change = (z - surface.get_value(row, col)).abs();
+                if change > max_change {
+                    max_change = change;
+                }
+                surface.set_value(row, col, z);
+            }
+        }
+        iterations += 1;
+        if max_change < tolerance {
+            break;
+        }
+    }
+    iterations
+}
+
+#[derive(PartialEq, Debug)]
+struct GridCell {
+    row: isize,
+    column: isize,
+    priority: f64,
+}
+
+impl Eq for GridCell {}
+
+impl PartialOrd for GridCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.priority.partial_cmp(&self.priority)
+    }
+}
+
+impl Ord for GridCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
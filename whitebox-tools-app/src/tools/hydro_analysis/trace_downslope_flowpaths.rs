@@ -66,7 +66,7 @@ impl TraceDownslopeFlowpaths {
 
         parameters.push(ToolParameter {
             name: "Input D8 Pointer File".to_owned(),
-            flags: vec!["--d8_pntr".to_owned()],
+            flags: vec!["--flow_dir".to_owned(), "--d8_pntr".to_owned()],
             description: "Input D8 pointer raster file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -84,7 +84,7 @@ impl TraceDownslopeFlowpaths {
 
         parameters.push(ToolParameter {
             name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -159,63 +159,17 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut seed_file = String::new();
-        let mut flowdir_file = String::new();
-        let mut output_file = String::new();
-        let mut esri_style = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut seed_file = parsed.get_string("seed_pts").unwrap_or_default();
+        let mut flowdir_file = parsed.get_string("d8_pntr").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let esri_style = parsed.get_bool("esri_pntr");
         let mut background_val = f64::NEG_INFINITY;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-seed_pts" || vec[0].to_lowercase() == "--seed_pts" {
-                if keyval {
-                    seed_file = vec[1].to_string();
-                } else {
-                    seed_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "--d8_pntr"
-                || vec[0].to_lowercase() == "-flow_dir"
-                || vec[0].to_lowercase() == "--flow_dir"
-            {
-                if keyval {
-                    flowdir_file = vec[1].to_string();
-                } else {
-                    flowdir_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-esri_pntr"
-                || vec[0].to_lowercase() == "--esri_pntr"
-                || vec[0].to_lowercase() == "--esri_style"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
-            } else if vec[0].to_lowercase() == "-zero_background"
-                || vec[0].to_lowercase() == "--zero_background"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    background_val = 0f64;
-                }
-            }
+        if parsed.get_bool("zero_background") {
+            background_val = 0f64;
         }
 
         if verbose {
@@ -229,20 +183,12 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !seed_file.contains(&sep) && !seed_file.contains("/") {
-            seed_file = format!("{}{}", working_directory, seed_file);
-        }
-        if !flowdir_file.contains(&sep) && !flowdir_file.contains("/") {
-            flowdir_file = format!("{}{}", working_directory, flowdir_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        seed_file = resolve_input_path(&seed_file, working_directory)?;
+        flowdir_file = resolve_input_path(&flowdir_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         // if verbose { println!("Reading destination data...") };
         // let seeds = Raster::new(&seed_file, "r")?;
@@ -288,11 +234,12 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
                 seed_cols.push(flowdir.get_column_from_x(record.points[i].x));
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (seeds.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Locating seed points: {}%", progress);
+                    reporter.progress("Locating seed points", progress);
                     old_progress = progress;
                 }
             }
@@ -365,7 +312,7 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
         for i in 0..seed_cols.len() {
             let row = seed_rows[i];
             let col = seed_cols[i];
-            if flowdir.get_value(row, col) != nodata {
+            if !is_nodata(flowdir.get_value(row, col), nodata) {
                 flag = false;
                 x = col;
                 y = row;
@@ -377,7 +324,7 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
                     }
                     // find its downslope neighbour
                     dir = flowdir.get_value(y, x);
-                    if dir != nodata && dir > 0.0 {
+                    if !is_nodata(dir, nodata) && dir > 0.0 {
                         // move x and y accordingly
                         x += dx[pntr_matches[dir as usize]];
                         y += dy[pntr_matches[dir as usize]];
@@ -386,10 +333,11 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / (seed_cols.len() - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -397,14 +345,15 @@ impl WhiteboxTool for TraceDownslopeFlowpaths {
 
         for row in 0..rows {
             for col in 0..columns {
-                if flowdir.get_value(row, col) == nodata {
+                if is_nodata(flowdir.get_value(row, col), nodata) {
                     output.set_value(row, col, nodata);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -79,7 +79,7 @@ impl UnnestBasins {
 
         parameters.push(ToolParameter {
             name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -144,52 +144,13 @@ impl WhiteboxTool for UnnestBasins {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut d8_file = String::new();
-        let mut pourpts_file = String::new();
-        let mut output_file = String::new();
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-d8_pntr" {
-                d8_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-pour_pts" {
-                pourpts_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("d8_pntr").unwrap_or_default();
+        let mut pourpts_file = parsed.get_string("pour_pts").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let esri_style = parsed.get_bool("esri_pntr");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -202,20 +163,12 @@ impl WhiteboxTool for UnnestBasins {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !d8_file.contains(&sep) && !d8_file.contains("/") {
-            d8_file = format!("{}{}", working_directory, d8_file);
-        }
-        if !pourpts_file.contains(&sep) && !pourpts_file.contains("/") {
-            pourpts_file = format!("{}{}", working_directory, pourpts_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        d8_file = resolve_input_path(&d8_file, working_directory)?;
+        pourpts_file = resolve_input_path(&pourpts_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let start = Instant::now();
 
@@ -258,10 +211,11 @@ impl WhiteboxTool for UnnestBasins {
             outlet_rows[outlet] = row;
             outlet_columns[outlet] = col;
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * outlet as f64 / pourpts.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Locating pour points: {}%", progress);
+                    reporter.progress("Locating pour points", progress);
                     old_progress = progress;
                 }
             }
@@ -300,7 +254,7 @@ impl WhiteboxTool for UnnestBasins {
         for row in 0..rows {
             for col in 0..columns {
                 z = pntr.get_value(row, col);
-                if z != pntr_nodata {
+                if !is_nodata(z, pntr_nodata) {
                     if z > 0.0 {
                         flow_dir.set_value(row, col, pntr_matches[z as usize]);
                     } else {
@@ -308,10 +262,11 @@ impl WhiteboxTool for UnnestBasins {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Initializing: {}%", progress);
+                    reporter.progress("Initializing", progress);
                     old_progress = progress;
                 }
             }
@@ -355,10 +310,11 @@ impl WhiteboxTool for UnnestBasins {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * outlet as f64 / pourpts.num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating outlet nesting order: {}%", progress);
+                    reporter.progress("Calculating outlet nesting order", progress);
                     old_progress = progress;
                 }
             }
@@ -440,13 +396,11 @@ impl WhiteboxTool for UnnestBasins {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Progress (Loop {} of {}): {}%",
-                            order, max_nesting_order, progress
-                        );
+                        reporter.progress(&format!("Progress (Loop {} of {})", order, max_nesting_order), progress);
                         old_progress = progress;
                     }
                 }
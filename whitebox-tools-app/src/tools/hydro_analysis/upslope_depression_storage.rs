@@ -9,17 +9,16 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::env;
 use std::f32;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool estimates the average upslope depression storage depth using the FD8 flow algorithm.
 /// The input DEM (`--dem`) need not be hydrologically corrected; the tool will internally map depression
@@ -49,7 +48,7 @@ impl UpslopeDepressionStorage {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -127,40 +126,11 @@ impl WhiteboxTool for UpslopeDepressionStorage {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -173,17 +143,11 @@ impl WhiteboxTool for UpslopeDepressionStorage {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -240,9 +204,9 @@ impl WhiteboxTool for UpslopeDepressionStorage {
         for row in 0..rows {
             for col in 0..columns {
                 z = filled.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z as f64, nodata as f64) {
                     for n in 0..8 {
-                        if filled.get_value(row + dy[n], col + dx[n]) == nodata {
+                        if is_nodata(filled.get_value(row + dy[n], col + dx[n]) as f64, nodata as f64) {
                             minheap.push(GridCell {
                                 row: row,
                                 column: col,
@@ -259,10 +223,11 @@ impl WhiteboxTool for UpslopeDepressionStorage {
                     num_cells_visited += 1;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding edges: {}%", progress);
+                    reporter.progress("Finding edges", progress);
                     old_progress = progress;
                 }
             }
@@ -304,11 +269,12 @@ impl WhiteboxTool for UpslopeDepressionStorage {
                     num_cells_visited += 1;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * num_cells_visited as f64 / (rows * columns - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Filling: {}%", progress);
+                    reporter.progress("Filling", progress);
                     old_progress = progress;
                 }
             }
@@ -429,17 +395,13 @@ impl WhiteboxTool for UpslopeDepressionStorage {
         // calculate the number of inflowing cells
         let filled = Arc::new(filled);
         let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let filled = filled.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let mut z: f32;
@@ -449,18 +411,20 @@ impl WhiteboxTool for UpslopeDepressionStorage {
                     let mut data: Vec<i8> = vec![-1i8; columns as usize];
                     for col in 0..columns {
                         z = filled.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z as f64, nodata as f64) {
                             count = 0i8;
                             for n in 0..8 {
                                 zn = filled.get_value(row + dy[n], col + dx[n]);
-                                if zn > z && zn != nodata {
+                                if zn > z && !is_nodata(zn as f64, nodata as f64) {
                                     count += 1;
                                 }
                             }
                             data[col as usize] = count;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -478,10 +442,11 @@ impl WhiteboxTool for UpslopeDepressionStorage {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Num. inflowing neighbours: {}%", progress);
+                    reporter.progress("Num. inflowing neighbours", progress);
                     old_progress = progress;
                 }
             }
@@ -510,7 +475,7 @@ impl WhiteboxTool for UpslopeDepressionStorage {
                 rn = row + dy[n];
                 cn = col + dx[n];
                 zn = filled.get_value(rn, cn);
-                if zn < z && zn != nodata {
+                if zn < z && !is_nodata(zn as f64, nodata as f64) {
                     weights[n] = (z - zn).powf(exponent);
                     total_weights += weights[n];
                     downslope[n] = true;
@@ -535,11 +500,12 @@ impl WhiteboxTool for UpslopeDepressionStorage {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_solved += 1;
                 progress = (100.0_f64 * num_solved as f64 / (rows * columns - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Flow accumulation: {}%", progress);
+                    reporter.progress("Flow accumulation", progress);
                     old_progress = progress;
                 }
             }
@@ -548,7 +514,7 @@ impl WhiteboxTool for UpslopeDepressionStorage {
         for row in 0..rows {
             for col in 0..columns {
                 z = filled.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z as f64, nodata as f64) {
                     output.set_value(
                         row,
                         col,
@@ -556,11 +522,12 @@ impl WhiteboxTool for UpslopeDepressionStorage {
                     );
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * num_cells_visited as f64 / (rows * columns - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Final calculation: {}%", progress);
+                    reporter.progress("Final calculation", progress);
                     old_progress = progress;
                 }
             }
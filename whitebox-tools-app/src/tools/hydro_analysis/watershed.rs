@@ -111,7 +111,7 @@ impl Watershed {
 
         parameters.push(ToolParameter {
             name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -176,52 +176,13 @@ impl WhiteboxTool for Watershed {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut d8_file = String::new();
-        let mut pourpts_file = String::new();
-        let mut output_file = String::new();
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-d8_pntr" {
-                d8_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-pour_pts" {
-                pourpts_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("d8_pntr").unwrap_or_default();
+        let mut pourpts_file = parsed.get_string("pour_pts").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let esri_style = parsed.get_bool("esri_pntr");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -234,20 +195,12 @@ impl WhiteboxTool for Watershed {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !d8_file.contains(&sep) && !d8_file.contains("/") {
-            d8_file = format!("{}{}", working_directory, d8_file);
-        }
-        if !pourpts_file.contains(&sep) && !pourpts_file.contains("/") {
-            pourpts_file = format!("{}{}", working_directory, pourpts_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        d8_file = resolve_input_path(&d8_file, working_directory)?;
+        pourpts_file = resolve_input_path(&pourpts_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -322,11 +275,12 @@ impl WhiteboxTool for Watershed {
                 let col = pntr.get_column_from_x(record.points[0].x);
                 output.set_value(row, col, (record_num + 1) as f64);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress =
                         (100.0_f64 * record_num as f64 / (pourpts.num_records - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Locating pour points: {}%", progress);
+                        reporter.progress("Locating pour points", progress);
                         old_progress = progress;
                     }
                 }
@@ -335,7 +289,7 @@ impl WhiteboxTool for Watershed {
             for row in 0..rows {
                 for col in 0..columns {
                     z = pntr[(row, col)];
-                    if z != pntr_nodata {
+                    if !is_nodata(z, pntr_nodata) {
                         if z > 0.0 {
                             flow_dir.set_value(row, col, pntr_matches[z as usize]);
                         } else {
@@ -345,10 +299,11 @@ impl WhiteboxTool for Watershed {
                         output.set_value(row, col, nodata);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Initializing: {}%", progress);
+                        reporter.progress("Initializing", progress);
                         old_progress = progress;
                     }
                 }
@@ -361,14 +316,14 @@ impl WhiteboxTool for Watershed {
             if pourpts.configs.rows != pntr.configs.rows
                 || pourpts.configs.columns != pntr.configs.columns
             {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                    "The input files must have the same number of rows and columns and spatial extent."));
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
             }
+            warn_if_crs_differs(&pourpts, &pntr, verbose);
 
             for row in 0..rows {
                 for col in 0..columns {
                     z = pntr.get_value(row, col);
-                    if z != pntr_nodata {
+                    if !is_nodata(z, pntr_nodata) {
                         if z > 0.0 {
                             flow_dir.set_value(row, col, pntr_matches[z as usize]);
                         } else {
@@ -378,14 +333,15 @@ impl WhiteboxTool for Watershed {
                         output.set_value(row, col, nodata);
                     }
                     z = pourpts.get_value(row, col);
-                    if z != nodata && z > 0.0 {
+                    if !is_nodata(z, nodata) && z > 0.0 {
                         output.set_value(row, col, z);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Initializing: {}%", progress);
+                        reporter.progress("Initializing", progress);
                         old_progress = progress;
                     }
                 }
@@ -445,10 +401,11 @@ impl WhiteboxTool for Watershed {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -9,16 +9,15 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
 use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a type of adaptive filter on a raster image. An adaptive filter can be used to reduce
 /// the level of random noise (shot noise) in an image. The algorithm operates by calculating the average
@@ -107,7 +106,7 @@ impl AdaptiveFilter {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=output.tif --filter=25 --threshold = 2.0", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=output.tif --filter=25 --threshold=2.0", short_exe, name).replace("*", &sep);
 
         AdaptiveFilter {
             name: name,
@@ -152,96 +151,17 @@ impl WhiteboxTool for AdaptiveFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size_x = 11usize;
-        let mut filter_size_y = 11usize;
-        let mut threshold = 2.0f64;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-filter" {
-                if keyval {
-                    filter_size_x = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_x = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-                filter_size_y = filter_size_x;
-            } else if flag_val == "-filterx" {
-                if keyval {
-                    filter_size_x = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_x = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-filtery" {
-                if keyval {
-                    filter_size_y = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_y = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-threshold" {
-                if keyval {
-                    threshold = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    threshold = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size_x = parsed.get_usize("filterx")?.unwrap_or(11usize);
+        let mut filter_size_y = parsed.get_usize("filtery")?.unwrap_or(11usize);
+        let threshold = parsed.get_f64("threshold").unwrap_or(2.0f64);
+        if let Some(value) = parsed.get_silent_f64("filter")? {
+            filter_size_x = value as usize;
+            filter_size_y = filter_size_x;
         }
 
         if verbose {
@@ -255,8 +175,6 @@ impl WhiteboxTool for AdaptiveFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         if filter_size_x < 3 {
             filter_size_x = 3;
         }
@@ -277,12 +195,8 @@ impl WhiteboxTool for AdaptiveFilter {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -321,7 +235,7 @@ impl WhiteboxTool for AdaptiveFilter {
         } else {
             Box::new(|row: isize, col: isize| -> f64 {
                 let value = input.get_value(row, col);
-                if value != nodata {
+                if !is_nodata(value, nodata) {
                     return value2i(value);
                 }
                 nodata
@@ -339,7 +253,7 @@ impl WhiteboxTool for AdaptiveFilter {
             sum_n = 0;
             for col in 0..columns {
                 val = input_fn(row, col);
-                if val == nodata {
+                if is_nodata(val, nodata) {
                     val = 0f64;
                 } else {
                     val -= min_val;
@@ -360,10 +274,11 @@ impl WhiteboxTool for AdaptiveFilter {
                     integral_n[(row, col)] = sum_n;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating integral images: {}%", progress);
+                    reporter.progress("Calculating integral images", progress);
                     old_progress = progress;
                 }
             }
@@ -373,12 +288,8 @@ impl WhiteboxTool for AdaptiveFilter {
         let i2 = Arc::new(integral2); // wrap integral2 in an Arc
         let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
         let mut output = Raster::initialize_using_file(&output_file, &input);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
@@ -386,13 +297,13 @@ impl WhiteboxTool for AdaptiveFilter {
             let i2 = i2.clone();
             let i_n = i_n.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
                     Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -405,7 +316,7 @@ impl WhiteboxTool for AdaptiveFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -437,7 +348,7 @@ impl WhiteboxTool for AdaptiveFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             x1 = col - midpoint_x - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -475,7 +386,9 @@ impl WhiteboxTool for AdaptiveFilter {
                         }
                     }
 
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -483,10 +396,11 @@ impl WhiteboxTool for AdaptiveFilter {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to reduce colour bias in a colour composite image based on the
 /// technique described by Liu (1991). Colour bias is a common phenomena with colour images
@@ -136,52 +135,12 @@ impl WhiteboxTool for BalanceContrastEnhancement {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut e = 100f64;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-band_mean" {
-                if keyval {
-                    e = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    e = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let e = parsed.get_f64("band_mean").unwrap_or(100f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -194,17 +153,11 @@ impl WhiteboxTool for BalanceContrastEnhancement {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading image data...")
@@ -220,17 +173,15 @@ impl WhiteboxTool for BalanceContrastEnhancement {
         let l = 0f64;
         let h = 255f64;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut z: f64;
                 let (mut r, mut g, mut b): (u32, u32, u32);
                 let mut num_pixels = 0f64;
@@ -247,9 +198,12 @@ impl WhiteboxTool for BalanceContrastEnhancement {
                 let mut b_e = 0f64;
                 let mut b_sqr_total = 0f64;
                 for row in (0..rows).filter(|rt| rt % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             num_pixels += 1f64;
                             r = z as u32 & 0xFF;
                             g = (z as u32 >> 8) & 0xFF;
@@ -284,7 +238,7 @@ impl WhiteboxTool for BalanceContrastEnhancement {
                         }
                     }
                 }
-                tx.send((
+                if tx.send((
                     r_l,
                     r_h,
                     r_e,
@@ -298,8 +252,9 @@ impl WhiteboxTool for BalanceContrastEnhancement {
                     b_e,
                     b_sqr_total,
                     num_pixels,
-                ))
-                .unwrap();
+                )).is_err() {
+                    return;
+                }
             });
         }
 
@@ -363,10 +318,11 @@ impl WhiteboxTool for BalanceContrastEnhancement {
 
             num_pixels += tnum_pixels;
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tid as f64 / (num_procs - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -399,7 +355,7 @@ impl WhiteboxTool for BalanceContrastEnhancement {
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let (mut r, mut g, mut b, mut a): (u32, u32, u32, u32);
                 let (mut r_out, mut g_out, mut b_out): (u32, u32, u32);
@@ -408,7 +364,7 @@ impl WhiteboxTool for BalanceContrastEnhancement {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             r = z as u32 & 0xFF;
                             g = (z as u32 >> 8) & 0xFF;
                             b = (z as u32 >> 16) & 0xFF;
@@ -446,7 +402,9 @@ impl WhiteboxTool for BalanceContrastEnhancement {
                                 ((a << 24) | (b_out << 16) | (g_out << 8) | r_out) as f64;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -457,10 +415,11 @@ impl WhiteboxTool for BalanceContrastEnhancement {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
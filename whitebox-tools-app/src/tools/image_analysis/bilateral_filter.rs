@@ -8,15 +8,14 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to perform an edge-preserving smoothing filter, or bilateral filter, on an image. A bilateral
 /// filter can be used to emphasize the longer-range variability in an image, effectively acting to smooth the image,
@@ -152,66 +151,14 @@ impl WhiteboxTool for BilateralFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut filter_size = 0usize;
-        let mut sigma_dist = 0.75;
-        let mut sigma_int = 1.0;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-sigma_dist" {
-                if keyval {
-                    sigma_dist = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    sigma_dist = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-sigma_int" {
-                if keyval {
-                    sigma_int = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    sigma_int = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let mut sigma_dist = parsed.get_f64("sigma_dist").unwrap_or(0.75);
+        let mut sigma_int = parsed.get_f64("sigma_int").unwrap_or(1.0);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -224,14 +171,8 @@ impl WhiteboxTool for BilateralFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if sigma_dist < 0.5 {
             sigma_dist = 0.5;
@@ -320,12 +261,8 @@ impl WhiteboxTool for BilateralFilter {
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
@@ -333,13 +270,13 @@ impl WhiteboxTool for BilateralFilter {
             let dy = dy.clone();
             let weights_d = weights_d.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
                     Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -352,7 +289,7 @@ impl WhiteboxTool for BilateralFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -371,14 +308,14 @@ impl WhiteboxTool for BilateralFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             //fill weights_i with the appropriate intensity weights
                             sum = 0.0;
                             for a in 0..num_pixels_in_filter {
                                 x = col + dx[a];
                                 y = row + dy[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata(zn, nodata) {
                                     weight = recip_root_2_pi_times_sigma_i
                                         * (-1.0 * ((zn - z) * (zn - z)) / two_sigma_sqr_i).exp();
                                     weight *= weights_d[a];
@@ -392,7 +329,7 @@ impl WhiteboxTool for BilateralFilter {
                                 x = col + dx[a];
                                 y = row + dy[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata(zn, nodata) {
                                     z_final += weights_i[a] * zn / sum;
                                 }
                             }
@@ -401,7 +338,9 @@ impl WhiteboxTool for BilateralFilter {
                         }
                     }
 
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -409,10 +348,11 @@ impl WhiteboxTool for BilateralFilter {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,307 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool creates a virtual mosaic (`--output`), a small JSON file with the extension .wbvrt that
+/// lists a set of input rasters (`--inputs`), e.g. the tiles of a DEM, along with their extents. A
+/// virtual mosaic can be used as the input raster of any tool, which reads it as a single seamless
+/// raster covering the extents of all of its members, such that tiled data can be analyzed, e.g. by
+/// `FlowAccumulation`, without first being mosaicked into a new, and possibly very large, file. Only
+/// the members themselves are stored on disk, and the virtual mosaic is unaffected by changes to
+/// their values.
+///
+/// As with the `Mosaic` tool, the order of the input files is important: where members overlap, the
+/// cells of the mosaic are assigned the values of the last member in the list to possess valid data.
+/// Members are sampled by nearest neighbour onto the grid of the mosaic, which has the cell size of
+/// the first member, and so the members should share a grid, as tiles do. The members must share a
+/// coordinate reference system. When the `--inputs` parameter is left unspecified, the tool will use
+/// all of the *.tif*, *.tiff*, *.rdc*, *.flt*, *.sdat*, and *.dep* files located in the working
+/// directory.
+///
+/// Members located within the directory of the virtual mosaic are listed by their paths relative to
+/// it, such that the mosaic and its members can be moved together. Virtual mosaics are read-only; a
+/// tool's output can't be a virtual mosaic.
+///
+/// # See Also
+/// `Mosaic`, `MosaicWithFeathering`
+pub struct BuildVirtualMosaic {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl BuildVirtualMosaic {
+    pub fn new() -> BuildVirtualMosaic {
+        // public constructor
+        let name = "BuildVirtualMosaic".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Creates a virtual mosaic (.wbvrt) of rasters that tools can read as a single input."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output virtual mosaic (.wbvrt) file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='tile1.tif;tile2.tif;tile3.tif' -o=dem.wbvrt", short_exe, name).replace("*", &sep);
+
+        BuildVirtualMosaic {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for BuildVirtualMosaic {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_files = parsed.get_list("inputs");
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if !output_file.to_lowercase().ends_with(".wbvrt") {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The output file must be a virtual mosaic, with the extension .wbvrt.",
+            ));
+        }
+
+        input_files.retain(|f| !f.trim().is_empty());
+        if input_files.is_empty() {
+            if working_directory.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput,
+                    "This tool must be run by specifying either the input files or a working directory."));
+            }
+            let supported_raster_extensions = [".tif", ".tiff", ".dep", ".rdc", ".flt", ".sdat"];
+            for entry in fs::read_dir(working_directory)? {
+                let s = entry?.path().to_string_lossy().to_string();
+                if supported_raster_extensions
+                    .iter()
+                    .any(|extension| s.to_lowercase().ends_with(extension))
+                {
+                    input_files.push(s);
+                }
+            }
+            input_files.sort();
+        }
+        if input_files.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one input raster is required to build a virtual mosaic.",
+            ));
+        }
+        for f in input_files.iter_mut() {
+            *f = resolve_input_path(f.trim(), working_directory)?;
+        }
+        output_file = resolve_path(&output_file, working_directory);
+
+        let start = Instant::now();
+
+        // The members are read to find their extents and coordinate reference systems.
+        if verbose {
+            println!("Reading data...")
+        };
+        let num_files = input_files.len();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().max_threads(num_files).num_threads();
+        let files = Arc::new(input_files.clone());
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let files = files.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                for i in (0..files.len()).filter(|i| i % num_procs == tid) {
+                    let configs = Raster::new(&files[i], "r").map(|r| r.configs);
+                    if tx.send((i, configs)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+        let mut member_configs: Vec<Option<RasterConfigs>> = vec![None; num_files];
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for n in 0..num_files {
+            let (i, configs) = rx.recv().expect("Error receiving data from thread.");
+            member_configs[i] = Some(configs.map_err(|e| {
+                Error::new(
+                    e.kind(),
+                    format!("The input file {} could not be read: {}", input_files[i], e),
+                )
+            })?);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * (n + 1) as f64 / num_files as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Reading inputs", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        let member_configs: Vec<RasterConfigs> = member_configs.into_iter().flatten().collect();
+
+        let first = &member_configs[0];
+        let mut members = Vec::with_capacity(num_files);
+        for (file, configs) in input_files.iter().zip(&member_configs) {
+            if configs.crs().matches(&first.crs()) == Some(false) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The inputs must share a coordinate reference system, but {} ({}) differs from {} ({}).",
+                        file, configs.crs(), input_files[0], first.crs()
+                    ),
+                ));
+            }
+            if verbose
+                && ((configs.resolution_x - first.resolution_x).abs() > 1e-9 * first.resolution_x
+                    || (configs.resolution_y - first.resolution_y).abs() > 1e-9 * first.resolution_y)
+            {
+                println!(
+                    "Warning: The cell size of {} differs from that of the first input and it will be resampled by nearest neighbour.",
+                    file
+                );
+            }
+            members.push(MosaicMember::from_configs(
+                &member_file_name(file, &output_file),
+                configs,
+            ));
+        }
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let mosaic = VirtualMosaic {
+            members: members,
+            nodata: None,
+            resolution: None,
+        };
+        let _ = match mosaic.write(&output_file) {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Number of members: {}", num_files);
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the file name by which a member is listed in a virtual mosaic, i.e. its path relative to
+/// the directory of the mosaic if it is located within it, and otherwise its full path.
+fn member_file_name(file: &str, mosaic_file: &str) -> String {
+    if let Some(dir) = path::Path::new(mosaic_file).parent() {
+        if !dir.as_os_str().is_empty() {
+            if let Ok(relative) = path::Path::new(file).strip_prefix(dir) {
+                return relative.to_string_lossy().replace('\\', "/");
+            }
+        }
+    }
+    file.to_string()
+}
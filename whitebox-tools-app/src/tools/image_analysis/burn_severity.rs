@@ -0,0 +1,656 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use whitebox_common::structures::Array2D;
+use whitebox_vector::*;
+use crate::tools::data_tools::RasterToVectorPolygons;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool maps the severity of a wildfire from pre-fire and post-fire multispectral imagery using the
+/// differenced normalized burn ratio (dNBR; Key and Benson, 2006) or the relativized dNBR (RdNBR; Miller
+/// and Thode, 2007). The normalized burn ratio (NBR) of each image is either calculated from its
+/// near-infrared and shortwave-infrared (approx. 2.2 &mu;m) bands (`--pre_nir`, `--pre_swir`, `--post_nir`,
+/// `--post_swir`), e.g. Landsat 8/9 bands 5 and 7 or Sentinel-2 bands B8 and B12, or supplied directly
+/// (`--pre_nbr`, `--post_nbr`), e.g. from the `SpectralIndices` tool. The NBR is a ratio, and so band
+/// values may be scaled reflectances, but products with an additive offset, such as Landsat Collection 2
+/// Level-2 data, should first be converted to reflectance, e.g. using `SpectralIndices` with `--offset`.
+///
+/// The indices are calculated, following convention, in units of NBR &times; 1000:
+///
+/// > dNBR = 1000 (NBR<sub>pre</sub> - NBR<sub>post</sub>) - *offset*
+/// >
+/// > RdNBR = dNBR / sqrt(|NBR<sub>pre</sub>|)
+///
+/// where the optional `--offset` is the mean dNBR of unburned areas outside of the fire perimeter, which
+/// accounts for phenological differences between the two images. The RdNBR removes the dependence of dNBR
+/// on the amount of pre-fire vegetation, and so is preferred for heterogeneous landscapes; values of
+/// |NBR<sub>pre</sub>| smaller than 0.001 are set to 0.001 to avoid division by zero. The index is
+/// selected using `--index` and written to the output raster (`--output`).
+///
+/// Severity classes are assigned using a list of ascending class breaks (`--breaks`), each of which is
+/// the lowest value of a class, and optional class names (`--names`). By default, the breaks are those
+/// of Key and Benson (2006) for dNBR:
+///
+/// | Class | Severity | dNBR |
+/// |-------|----------|------|
+/// | 1 | Enhanced regrowth, high | < -250 |
+/// | 2 | Enhanced regrowth, low | -250 to -101 |
+/// | 3 | Unburned | -100 to 99 |
+/// | 4 | Low | 100 to 269 |
+/// | 5 | Moderate-low | 270 to 439 |
+/// | 6 | Moderate-high | 440 to 659 |
+/// | 7 | High | &ge; 660 |
+///
+/// and those of Miller and Thode (2007) for RdNBR:
+///
+/// | Class | Severity | RdNBR |
+/// |-------|----------|-------|
+/// | 1 | Unchanged | < 69 |
+/// | 2 | Low | 69 to 315 |
+/// | 3 | Moderate | 316 to 640 |
+/// | 4 | High | &ge; 641 |
+///
+/// These thresholds were calibrated against field measurements in particular ecosystems, and locally
+/// calibrated breaks should be used where they are available. The classes are written to an optional
+/// raster (`--out_classes`) and, from it, to an optional polygon vector (`--polygons`) with the fields
+/// CLASS and SEVERITY. The area of each class, in map units squared and as a percentage of the
+/// classified area, is written to an optional CSV file (`--out_stats`). If the images are in geographic
+/// coordinates, the areas are estimated in square metres.
+///
+/// # References
+/// Key, C. H., & Benson, N. C. (2006). Landscape assessment (LA): sampling and analysis methods. In
+/// *FIREMON: Fire effects monitoring and inventory system*, USDA Forest Service General Technical Report
+/// RMRS-GTR-164-CD, LA-1-51.
+///
+/// Miller, J. D., & Thode, A. E. (2007). Quantifying burn severity in a heterogeneous landscape with a
+/// relative version of the delta Normalized Burn Ratio (dNBR). *Remote Sensing of Environment*, 109(1),
+/// 66-80.
+///
+/// # See Also
+/// `SpectralIndices`, `NormalizedDifferenceIndex`, `RasterToVectorPolygons`
+pub struct BurnSeverity {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl BurnSeverity {
+    pub fn new() -> BurnSeverity {
+        // public constructor
+        let name = "BurnSeverity".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Maps wildfire burn severity classes from pre- and post-fire imagery using dNBR or RdNBR.".to_string();
+
+        let mut parameters = vec![];
+        for (period, label) in [("pre", "Pre-fire"), ("post", "Post-fire")] {
+            parameters.push(ToolParameter {
+                name: format!("Input {} NIR Band File", label),
+                flags: vec![format!("--{}_nir", period)],
+                description: format!("Input {} near-infrared band raster file.", label.to_lowercase()),
+                parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+                default_value: None,
+                optional: true,
+            });
+
+            parameters.push(ToolParameter {
+                name: format!("Input {} SWIR Band File", label),
+                flags: vec![format!("--{}_swir", period)],
+                description: format!("Input {} shortwave-infrared (approx. 2.2 um) band raster file.", label.to_lowercase()),
+                parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+                default_value: None,
+                optional: true,
+            });
+
+            parameters.push(ToolParameter {
+                name: format!("Input {} NBR File", label),
+                flags: vec![format!("--{}_nbr", period)],
+                description: format!("Input {} normalized burn ratio raster file, used instead of the bands.", label.to_lowercase()),
+                parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+                default_value: None,
+                optional: true,
+            });
+        }
+
+        parameters.push(ToolParameter {
+            name: "Output Index File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output dNBR or RdNBR raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Severity Index".to_owned(),
+            flags: vec!["--index".to_owned()],
+            description: "Severity index; either 'dnbr' or 'rdnbr'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["dnbr".to_owned(), "rdnbr".to_owned()]),
+            default_value: Some("dnbr".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "dNBR Offset".to_owned(),
+            flags: vec!["--offset".to_owned()],
+            description: "Mean dNBR of unburned areas, subtracted from the dNBR.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Class Breaks (optional)".to_owned(),
+            flags: vec!["--breaks".to_owned()],
+            description: "Ascending severity class breaks, e.g. '100;270;440;660'; defaults to the standard breaks of the index.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Class Names (optional)".to_owned(),
+            flags: vec!["--names".to_owned()],
+            description: "Names of the severity classes, one more than the number of breaks.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Severity Class File (optional)".to_owned(),
+            flags: vec!["--out_classes".to_owned()],
+            description: "Optional output severity class raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Severity Polygons File (optional)".to_owned(),
+            flags: vec!["--polygons".to_owned()],
+            description: "Optional output severity class polygons file; requires --out_classes.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Class Statistics File (optional)".to_owned(),
+            flags: vec!["--out_stats".to_owned()],
+            description: "Optional output CSV file of the area of each severity class.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --pre_nir=pre_b5.tif --pre_swir=pre_b7.tif --post_nir=post_b5.tif --post_swir=post_b7.tif -o=dnbr.tif --out_classes=severity.tif --polygons=severity.shp --out_stats=severity.csv
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --pre_nbr=pre_NBR.tif --post_nbr=post_NBR.tif -o=rdnbr.tif --index=rdnbr --offset=12.0 --out_classes=severity.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        BurnSeverity {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for BurnSeverity {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        // pre-fire and post-fire NIR, SWIR and NBR files
+        let mut band_files = [[String::new(), String::new(), String::new()], [String::new(), String::new(), String::new()]];
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut index = String::from("dnbr");
+        let offset = parsed.get_f64("offset").unwrap_or(0f64);
+        let breaks_str = parsed.get_string("breaks").unwrap_or_default();
+        let names_str = parsed.get_string("names").unwrap_or_default();
+        let mut classes_file = parsed.get_string("out_classes").unwrap_or_default();
+        let mut polygons_file = parsed.get_string("polygons").unwrap_or_default();
+        let mut stats_file = parsed.get_string("out_stats").unwrap_or_default();
+
+        if let Some(value) = parsed.get_string("pre_nir") {
+            band_files[0][0] = value;
+        }
+        if let Some(value) = parsed.get_string("pre_swir") {
+            band_files[0][1] = value;
+        }
+        if let Some(value) = parsed.get_string("pre_nbr") {
+            band_files[0][2] = value;
+        }
+        if let Some(value) = parsed.get_string("post_nir") {
+            band_files[1][0] = value;
+        }
+        if let Some(value) = parsed.get_string("post_swir") {
+            band_files[1][1] = value;
+        }
+        if let Some(value) = parsed.get_string("post_nbr") {
+            band_files[1][2] = value;
+        }
+        if let Some(value) = parsed.get_string("index") {
+            index = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let relativized = if index.contains("rdnbr") {
+            true
+        } else if index.contains("dnbr") {
+            false
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --index parameter must be either 'dnbr' or 'rdnbr'.",
+            ));
+        };
+
+        // Class breaks and names
+        let (breaks, names): (Vec<f64>, Vec<&str>) = if breaks_str.trim().is_empty() {
+            if relativized {
+                (
+                    vec![69f64, 316f64, 641f64],
+                    vec!["Unchanged", "Low", "Moderate", "High"],
+                )
+            } else {
+                (
+                    vec![-250f64, -100f64, 100f64, 270f64, 440f64, 660f64],
+                    vec![
+                        "Enhanced regrowth, high",
+                        "Enhanced regrowth, low",
+                        "Unburned",
+                        "Low",
+                        "Moderate-low",
+                        "Moderate-high",
+                        "High",
+                    ],
+                )
+            }
+        } else {
+            let mut breaks = vec![];
+            for b in breaks_str.split(|c| c == ';' || c == ',') {
+                if !b.trim().is_empty() {
+                    breaks.push(b.trim().parse::<f64>().map_err(|_| {
+                        Error::new(ErrorKind::InvalidInput, format!("Invalid class break '{}'.", b))
+                    })?);
+                }
+            }
+            if breaks.is_empty() || breaks.windows(2).any(|w| w[1] <= w[0]) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The class breaks must be a list of ascending values.",
+                ));
+            }
+            (breaks, vec![])
+        };
+        let mut names = if names.is_empty() {
+            (1..=breaks.len() + 1).map(|c| format!("Class {}", c)).collect::<Vec<String>>()
+        } else {
+            names.iter().map(|n| n.to_string()).collect::<Vec<String>>()
+        };
+        if !names_str.trim().is_empty() {
+            names = names_str.split(';').map(|n| n.trim().to_string()).collect();
+            if names.len() != breaks.len() + 1 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("{} class names are required for {} class breaks.", breaks.len() + 1, breaks.len()),
+                ));
+            }
+        }
+        let num_classes = breaks.len() + 1;
+        if num_classes > 254 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "There can be no more than 254 severity classes.",
+            ));
+        }
+
+        if !polygons_file.is_empty() && classes_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The severity polygons are created from the class raster; an --out_classes file must also be specified.",
+            ));
+        }
+
+        // Read the inputs; each image needs either an NBR raster or both bands.
+        if verbose {
+            println!("Reading data...")
+        };
+        let mut primary: Option<Raster> = None;
+        let mut inputs: Vec<Vec<Raster>> = vec![];
+        for (period, files) in ["pre-fire", "post-fire"].iter().zip(band_files.iter_mut()) {
+            let needed = if !files[2].trim().is_empty() {
+                vec![2]
+            } else if !files[0].trim().is_empty() && !files[1].trim().is_empty() {
+                vec![0, 1]
+            } else {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Either the {0} NBR or both the {0} NIR and SWIR bands must be specified.", period),
+                ));
+            };
+            let mut rasters = vec![];
+            for k in needed {
+                files[k] = resolve_input_path(&files[k], working_directory)?;
+                let raster = match &primary {
+                    Some(p) => read_secondary_raster(&files[k], p, false, true, verbose)?,
+                    None => Raster::new(&files[k], "r")?,
+                };
+                if primary.is_none() {
+                    primary = Some(Raster::new(&files[k], "r")?);
+                }
+                rasters.push(raster);
+            }
+            inputs.push(rasters);
+        }
+        let primary = primary.unwrap();
+        output_file = resolve_path(&output_file, working_directory);
+
+        let start = Instant::now();
+
+        let rows = primary.configs.rows as isize;
+        let columns = primary.configs.columns as isize;
+
+        // Returns the NBR of an image at a cell, or None for NoData or undefined values.
+        let nbr = |rasters: &Vec<Raster>, row: isize, col: isize| -> Option<f64> {
+            if rasters.len() == 1 {
+                let z = rasters[0].get_value(row, col);
+                if is_nodata(z, rasters[0].configs.nodata) {
+                    return None;
+                }
+                return Some(z);
+            }
+            let nir = rasters[0].get_value(row, col);
+            let swir = rasters[1].get_value(row, col);
+            if is_nodata(nir, rasters[0].configs.nodata) || is_nodata(swir, rasters[1].configs.nodata) || nir + swir == 0f64 {
+                return None;
+            }
+            Some((nir - swir) / (nir + swir))
+        };
+
+        let out_nodata = -32768f64;
+        let mut output = Raster::initialize_using_file(&output_file, &primary);
+        output.configs.data_type = DataType::F32;
+        output.configs.nodata = out_nodata;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "spectrum.plt".to_string();
+        output.reinitialize_values(out_nodata);
+
+        let mut classes: Array2D<u8> = Array2D::new(rows, columns, 255u8, 255u8)?;
+        let mut class_cells = vec![0usize; num_classes];
+        let mut class_area = vec![0f64; num_classes];
+        let is_geographic = primary.is_in_geographic_coordinates();
+        let mut cell_area = primary.configs.resolution_x * primary.configs.resolution_y;
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for row in 0..rows {
+            if is_geographic {
+                let mid_lat = primary.get_y_from_row(row).to_radians();
+                cell_area = primary.configs.resolution_x * 111_111.0 * mid_lat.cos()
+                    * primary.configs.resolution_y * 111_111.0;
+            }
+            for col in 0..columns {
+                let (pre, post) = match (nbr(&inputs[0], row, col), nbr(&inputs[1], row, col)) {
+                    (Some(pre), Some(post)) => (pre, post),
+                    _ => continue,
+                };
+                let mut z = 1000f64 * (pre - post) - offset;
+                if relativized {
+                    z /= pre.abs().max(0.001).sqrt();
+                }
+                output.set_value(row, col, z);
+                let class = breaks.iter().filter(|&&b| z >= b).count();
+                classes.set_value(row, col, (class + 1) as u8);
+                class_cells[class] += 1;
+                class_area[class] += cell_area;
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let index_name = if relativized { "RdNBR" } else { "dNBR" };
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Index: {}", index_name));
+        output.add_metadata_entry(format!("dNBR offset: {}", offset));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !classes_file.is_empty() {
+            classes_file = resolve_path(&classes_file, working_directory);
+            let mut output = Raster::initialize_using_file(&classes_file, &primary);
+            output.configs.data_type = DataType::U8;
+            output.configs.nodata = 255f64;
+            output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            output.configs.palette = "qual.plt".to_string();
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    output.set_value(row, col, classes.get_value(row, col) as f64);
+                }
+            }
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Index: {}", index_name));
+            for c in 0..num_classes {
+                output.add_metadata_entry(format!("Class {}: {}", c + 1, names[c]));
+            }
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Severity class file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if !polygons_file.is_empty() {
+            if verbose {
+                println!("Creating severity polygons...")
+            };
+            polygons_file = resolve_path(&polygons_file, working_directory);
+            RasterToVectorPolygons::new().run(
+                vec![
+                    format!("--input={}", classes_file),
+                    format!("--output={}", polygons_file),
+                ],
+                working_directory,
+                false,
+                reporter,
+            )?;
+
+            // Replace the attributes of the polygons with the class numbers and names.
+            let input = Shapefile::read(&polygons_file)?;
+            let mut output =
+                Shapefile::initialize_using_file(&polygons_file, &input, input.header.shape_type, false)?;
+            output
+                .attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+            output
+                .attributes
+                .add_field(&AttributeField::new("CLASS", FieldDataType::Int, 4u8, 0u8));
+            output.attributes.add_field(&AttributeField::new(
+                "SEVERITY",
+                FieldDataType::Text,
+                names.iter().map(|n| n.len()).max().unwrap_or(10).max(10).min(254) as u8,
+                0u8,
+            ));
+            for record_num in 0..input.num_records {
+                reporter.check_cancelled()?;
+                let record = input.get_record(record_num);
+                let class = match input.attributes.get_value(record_num, "VALUE") {
+                    FieldData::Real(v) => v.round() as usize,
+                    FieldData::Int(v) => v as usize,
+                    _ => 0,
+                };
+                if class < 1 || class > num_classes {
+                    continue;
+                }
+                output.add_record(record.clone());
+                output.attributes.add_record(
+                    vec![
+                        FieldData::Int(output.num_records as i32),
+                        FieldData::Int(class as i32),
+                        FieldData::Text(names[class - 1].clone()),
+                    ],
+                    false,
+                );
+            }
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Severity polygons file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        let total_area: f64 = class_area.iter().sum();
+        if !stats_file.is_empty() {
+            stats_file = resolve_path(&stats_file, working_directory);
+            let f = File::create(&stats_file)?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all("CLASS,SEVERITY,LOWER,UPPER,CELLS,AREA,PERCENT\n".as_bytes())?;
+            for c in 0..num_classes {
+                let lower = if c > 0 { breaks[c - 1].to_string() } else { String::new() };
+                let upper = if c < breaks.len() { breaks[c].to_string() } else { String::new() };
+                writer.write_all(
+                    format!(
+                        "{},\"{}\",{},{},{},{:.2},{:.3}\n",
+                        c + 1,
+                        names[c],
+                        lower,
+                        upper,
+                        class_cells[c],
+                        class_area[c],
+                        if total_area > 0f64 { 100f64 * class_area[c] / total_area } else { 0f64 }
+                    )
+                    .as_bytes(),
+                )?;
+            }
+            let _ = writer.flush();
+        }
+
+        if verbose {
+            if is_geographic {
+                println!("Warning: the input files do not appear to be in a projected coordinate system. Area values are only estimates, in square metres.");
+            }
+            for c in 0..num_classes {
+                println!(
+                    "Class {} ({}): {:.2} ({:.1}%)",
+                    c + 1,
+                    names[c],
+                    class_area[c],
+                    if total_area > 0f64 { 100f64 * class_area[c] / total_area } else { 0f64 }
+                );
+            }
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -155,54 +155,13 @@ impl WhiteboxTool for ChangeVectorAnalysis {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input1_files_str = String::new();
-        let mut input2_files_str = String::new();
-        let mut magnitude_file = String::new();
-        let mut direction_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-date1" {
-                input1_files_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-date2" {
-                input2_files_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-magnitude" {
-                magnitude_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-direction" {
-                direction_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input1_files_str = parsed.get_string("date1").unwrap_or_default();
+        let input2_files_str = parsed.get_string("date2").unwrap_or_default();
+        let mut magnitude_file = parsed.get_string("magnitude").unwrap_or_default();
+        let mut direction_file = parsed.get_string("direction").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -217,17 +176,11 @@ impl WhiteboxTool for ChangeVectorAnalysis {
 
         let start = Instant::now();
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !magnitude_file.contains(&sep) && !magnitude_file.contains("/") {
-            magnitude_file = format!("{}{}", working_directory, magnitude_file);
-        }
-        if !direction_file.contains(&sep) && !direction_file.contains("/") {
-            direction_file = format!("{}{}", working_directory, direction_file);
-        }
+        magnitude_file = resolve_path(&magnitude_file, working_directory);
+        direction_file = resolve_path(&direction_file, working_directory);
 
         let mut cmd = input1_files_str.split(";");
         let mut input1_files = cmd.collect::<Vec<&str>>();
@@ -271,9 +224,7 @@ impl WhiteboxTool for ChangeVectorAnalysis {
         // We will need to read one of the files in to get the rows, columns, and nodata values
         // in order to create the output files.
         let mut input1_file = input1_files[0].trim().to_owned();
-        if !input1_file.contains(&sep) && !input1_file.contains("/") {
-            input1_file = format!("{}{}", working_directory, input1_file);
-        }
+        input1_file = resolve_input_path(&input1_file, working_directory)?;
         let input1 = Raster::new(&input1_file, "r")?;
         let rows = input1.configs.rows as isize;
         let columns = input1.configs.columns as isize;
@@ -299,14 +250,10 @@ impl WhiteboxTool for ChangeVectorAnalysis {
             }
             if !input1_files[i].trim().is_empty() && !input2_files[i].trim().is_empty() {
                 let mut input1_file = input1_files[i].trim().to_owned();
-                if !input1_file.contains(&sep) && !input1_file.contains("/") {
-                    input1_file = format!("{}{}", working_directory, input1_file);
-                }
+                input1_file = resolve_input_path(&input1_file, working_directory)?;
 
                 let mut input2_file = input2_files[i].trim().to_owned();
-                if !input2_file.contains(&sep) && !input2_file.contains("/") {
-                    input2_file = format!("{}{}", working_directory, input2_file);
-                }
+                input2_file = resolve_input_path(&input2_file, working_directory)?;
 
                 let input1 = Raster::new(&input1_file, "r")?;
                 let input2 = Raster::new(&input2_file, "r")?;
@@ -317,10 +264,7 @@ impl WhiteboxTool for ChangeVectorAnalysis {
                     || input2.configs.rows as isize != rows
                     || input2.configs.columns as isize != columns
                 {
-                    return Err(Error::new(
-                        ErrorKind::InvalidInput,
-                        "All of the input files must share the same extent (rows and columns).",
-                    ));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All of the input files must share the same extent (rows and columns)."));
                 }
 
                 let nodata1 = input1.configs.nodata;
@@ -332,7 +276,7 @@ impl WhiteboxTool for ChangeVectorAnalysis {
                     for col in 0..columns {
                         z1 = input1.get_value(row, col);
                         z2 = input2.get_value(row, col);
-                        if z1 != nodata1 && z2 != nodata2 {
+                        if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                             z = z2 - z1;
                             out_magnitude.increment(row, col, z * z);
                             if z >= 0f64 {
@@ -342,10 +286,11 @@ impl WhiteboxTool for ChangeVectorAnalysis {
                             nodata_detected.set_value(row, col, 1i8);
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -364,10 +309,11 @@ impl WhiteboxTool for ChangeVectorAnalysis {
                     out_direction.set_value(row, col, nodata);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
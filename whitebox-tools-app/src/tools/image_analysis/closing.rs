@@ -9,15 +9,14 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::collections::VecDeque;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a closing operation on an input greyscale image (`--input`). A
 /// [closing](https://en.wikipedia.org/wiki/Closing_(morphology)) is a mathematical morphology operation involving
@@ -142,83 +141,16 @@ impl WhiteboxTool for Closing {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size_x = 11usize;
-        let mut filter_size_y = 11usize;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-filter" {
-                if keyval {
-                    filter_size_x = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_x = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-                filter_size_y = filter_size_x;
-            } else if flag_val == "-filterx" {
-                if keyval {
-                    filter_size_x = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_x = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-filtery" {
-                if keyval {
-                    filter_size_y = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_y = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size_x = parsed.get_usize("filterx")?.unwrap_or(11usize);
+        let mut filter_size_y = parsed.get_usize("filtery")?.unwrap_or(11usize);
+        if let Some(value) = parsed.get_silent_f64("filter")? {
+            filter_size_x = value as usize;
+            filter_size_y = filter_size_x;
         }
 
         if verbose {
@@ -232,8 +164,6 @@ impl WhiteboxTool for Closing {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         if filter_size_x < 3 {
             filter_size_x = 3;
         }
@@ -254,12 +184,8 @@ impl WhiteboxTool for Closing {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -273,17 +199,13 @@ impl WhiteboxTool for Closing {
         let rows = input.configs.rows as isize;
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut z_n, mut z): (f64, f64);
                 let mut max_val: f64;
                 let (mut start_col, mut end_col, mut start_row, mut end_row): (
@@ -303,7 +225,7 @@ impl WhiteboxTool for Closing {
                             max_val = f64::NEG_INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input.get_value(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     if z_n > max_val {
                                         max_val = z_n;
                                     }
@@ -318,7 +240,7 @@ impl WhiteboxTool for Closing {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata(z_n, nodata) {
                                         if z_n > max_val {
                                             max_val = z_n;
                                         }
@@ -328,7 +250,7 @@ impl WhiteboxTool for Closing {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             max_val = f64::NEG_INFINITY;
                             for i in 0..filter_size_x {
                                 if filter_max_vals[i] > max_val {
@@ -340,7 +262,9 @@ impl WhiteboxTool for Closing {
                             }
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -349,10 +273,11 @@ impl WhiteboxTool for Closing {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             dilation.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -364,7 +289,7 @@ impl WhiteboxTool for Closing {
         for tid in 0..num_procs {
             let input = dilation.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut z_n, mut z): (f64, f64);
                 let mut min_val: f64;
                 let (mut start_col, mut end_col, mut start_row, mut end_row): (
@@ -384,7 +309,7 @@ impl WhiteboxTool for Closing {
                             min_val = f64::INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input.get_value(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     if z_n < min_val {
                                         min_val = z_n;
                                     }
@@ -399,7 +324,7 @@ impl WhiteboxTool for Closing {
                                 min_val = f64::INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -409,7 +334,7 @@ impl WhiteboxTool for Closing {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             min_val = f64::INFINITY;
                             for i in 0..filter_size_x {
                                 if filter_min_vals[i] < min_val {
@@ -421,7 +346,9 @@ impl WhiteboxTool for Closing {
                             }
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -430,10 +357,11 @@ impl WhiteboxTool for Closing {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,396 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::rendering::*;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool renders a single-band raster (`--input`), such as a DEM or a spectral index, as a colour
+/// image using a colour ramp, producing a visual product that can be shared and viewed without GIS
+/// software. The output (`--output`) is written as a PNG image if its file name has the extension .png,
+/// with a world file (.pgw) and, if the input's coordinate reference system is known, a .prj file that
+/// georeference it, or otherwise as a 32-bit RGBA colour raster (e.g. a GeoTIFF). NoData cells are
+/// transparent.
+///
+/// The colour ramp (`--palette`) is either one of the built-in ramps, `grey`, `viridis`, `magma`,
+/// `terrain`, `spectral`, `blue_white_red`, `brown_green` and `blues`, or a palette file in the format
+/// of GDAL's color-relief palettes, in which each line contains a value and a red, green, blue and
+/// (optionally) alpha value (0-255), e.g.:
+///
+/// ```text
+/// # elevation  red  green  blue
+/// 0     0   97  71
+/// 50%   232 215 125
+/// 100%  255 255 255
+/// nv    0   0   0   0
+/// ```
+///
+/// Values ending with '%' are percentages of the range of values that is rendered, other values are
+/// absolute, and the value `nv` sets the colour of NoData cells. Colours are interpolated linearly
+/// between the values of a ramp, and values beyond its ends are given the colours of its ends. The
+/// colours of a ramp may be reversed (`--reverse`).
+///
+/// The built-in ramps span the range of values that is rendered, which is, by default, the range of the
+/// input's values. This range may be set by `--min_value` and `--max_value`, or found by clipping the
+/// percentage `--clip` of the cells from each tail of the input's distribution, e.g. to keep a few
+/// outlying values from dominating the colours.
+///
+/// The image may optionally be blended with a hillshade raster (`--hillshade`), e.g. created using the
+/// `Hillshade` or `MultidirectionalHillshade` tools, to show the form of the terrain beneath the colours.
+/// The colours are darkened by the shading, in proportion to the `--blend` weight (0-1), where a weight
+/// of zero leaves the colours unshaded and a weight of one makes the fully shaded cells black. The
+/// hillshade must share the grid of the input, unless `--align=auto` is specified, in which case it is
+/// resampled to it.
+///
+/// # See Also
+/// `ExportColourComposite`, `CreateColourComposite`, `Hillshade`, `MultidirectionalHillshade`
+pub struct ColourizeRaster {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ColourizeRaster {
+    pub fn new() -> ColourizeRaster {
+        // public constructor
+        let name = "ColourizeRaster".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Renders a single-band raster as a colour image (RGBA raster or PNG) using a colour ramp."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output colour raster or PNG (.png) file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Palette".to_owned(),
+            flags: vec!["--palette".to_owned()],
+            description: "Built-in colour ramp name (e.g. 'viridis', 'terrain') or palette file.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("viridis".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reverse palette?".to_owned(),
+            flags: vec!["--reverse".to_owned()],
+            description: "Optional flag to reverse the colours of the palette.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Value (optional)".to_owned(),
+            flags: vec!["--min_value".to_owned()],
+            description: "Value at the low end of the colour ramp.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Value (optional)".to_owned(),
+            flags: vec!["--max_value".to_owned()],
+            description: "Value at the high end of the colour ramp.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tail Clip Percent".to_owned(),
+            flags: vec!["--clip".to_owned()],
+            description: "Percentage of cells clipped from each tail of the distribution to find the range of values.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Hillshade File (optional)".to_owned(),
+            flags: vec!["--hillshade".to_owned()],
+            description: "Input hillshade raster blended with the colours.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Hillshade Blend Weight".to_owned(),
+            flags: vec!["--blend".to_owned()],
+            description: "Weight (0-1) of the hillshade in the blended image.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Alignment".to_owned(),
+            flags: vec!["--align".to_owned()],
+            description: "Input alignment mode; 'auto' resamples the hillshade to the grid of the input.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["none".to_owned(), "auto".to_owned()]),
+            default_value: Some("none".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif -o=dem_colours.png --palette=terrain --clip=1.0 --hillshade=hillshade.tif --blend=0.4", short_exe, name).replace("*", &sep);
+
+        ColourizeRaster {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ColourizeRaster {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let palette = parsed.get_string("palette").unwrap_or("viridis".to_string());
+        let reverse = parsed.get_bool("reverse");
+        let min_value = parsed.get_f64("min_value");
+        let max_value = parsed.get_f64("max_value");
+        let clip = parsed.get_f64("clip").unwrap_or(0f64);
+        let mut hillshade_file = parsed.get_string("hillshade").unwrap_or_default();
+        let blend = parsed.get_f64("blend").unwrap_or(0.5f64);
+        let align = parsed.get_string("align").unwrap_or_default() == "auto";
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if clip < 0f64 || clip >= 50f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The tail clip percent must be between 0 and 50.",
+            ));
+        }
+        if blend < 0f64 || blend > 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The hillshade blend weight must be between 0 and 1.",
+            ));
+        }
+
+        let mut ramp = ColourRamp::new(&palette, working_directory)?;
+        if reverse {
+            ramp.reverse();
+        }
+        let nodata_colour = ramp.nodata_colour();
+
+        if verbose {
+            println!("Reading data...")
+        };
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        let input = Raster::new(&input_file, "r")?;
+        let hillshade = if !hillshade_file.is_empty() {
+            hillshade_file = resolve_input_path(&hillshade_file, working_directory)?;
+            Some(read_secondary_raster(&hillshade_file, &input, align, true, verbose)?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        // The range of values spanned by the colour ramp
+        let (mut low, mut high) = (0f64, 1f64);
+        if ramp.is_relative() {
+            if min_value.is_none() || max_value.is_none() {
+                let (clip_low, clip_high) = input.calculate_clip_values(clip);
+                low = clip_low;
+                high = clip_high;
+            }
+            low = min_value.unwrap_or(low);
+            high = max_value.unwrap_or(high);
+            if low > high {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The minimum value must be less than the maximum value.",
+                ));
+            }
+            if verbose {
+                println!("Range of values: {} to {}", low, high);
+            }
+        }
+        let stops = ramp.resolve(low, high);
+
+        let (shade_min, shade_range, shade_nodata) = match &hillshade {
+            Some(h) => {
+                let (min, max) = h.calculate_clip_values(0f64);
+                (min, (max - min).max(f64::EPSILON), h.configs.nodata)
+            }
+            None => (0f64, 1f64, 0f64),
+        };
+
+        let mut pixels: Vec<Rgba> = vec![TRANSPARENT; (rows * columns) as usize];
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                let i = (row * columns + col) as usize;
+                if is_nodata(z, nodata) {
+                    pixels[i] = nodata_colour;
+                    continue;
+                }
+                let mut c = ramp_colour(&stops, z);
+                if let Some(h) = &hillshade {
+                    let s = h.get_value(row, col);
+                    if !is_nodata(s, shade_nodata) {
+                        let shade = ((s - shade_min) / shade_range).max(0f64).min(1f64);
+                        let factor = 1f64 - blend + blend * shade;
+                        for k in 0..3 {
+                            c[k] *= factor;
+                        }
+                    }
+                }
+                pixels[i] = [
+                    c[0].round() as u8,
+                    c[1].round() as u8,
+                    c[2].round() as u8,
+                    c[3].round() as u8,
+                ];
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let mut metadata = vec![
+            format!("Created by whitebox_tools\' {} tool", self.get_tool_name()),
+            format!("Input file: {}", input_file),
+            format!("Palette: {}", palette),
+        ];
+        if ramp.is_relative() {
+            metadata.push(format!("Range of values: {} to {}", low, high));
+        }
+        if hillshade.is_some() {
+            metadata.push(format!("Hillshade file: {}", hillshade_file));
+            metadata.push(format!("Hillshade blend weight: {}", blend));
+        }
+        metadata.push(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match write_image(&output_file, &input, &pixels, metadata) {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
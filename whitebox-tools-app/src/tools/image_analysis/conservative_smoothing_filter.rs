@@ -8,16 +8,15 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::collections::VecDeque;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a conservative smoothing filter on a raster image. A conservative smoothing filter can be used
 /// to remove short-range variability in an image, effectively acting to smooth the image. It is particularly useful
@@ -148,83 +147,16 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size_x = 3usize;
-        let mut filter_size_y = 3usize;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-filter" {
-                if keyval {
-                    filter_size_x = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_x = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-                filter_size_y = filter_size_x;
-            } else if flag_val == "-filterx" {
-                if keyval {
-                    filter_size_x = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_x = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-filtery" {
-                if keyval {
-                    filter_size_y = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_y = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size_x = parsed.get_usize("filterx")?.unwrap_or(3usize);
+        let mut filter_size_y = parsed.get_usize("filtery")?.unwrap_or(3usize);
+        if let Some(value) = parsed.get_silent_f64("filter")? {
+            filter_size_x = value as usize;
+            filter_size_y = filter_size_x;
         }
 
         if verbose {
@@ -238,8 +170,6 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         if filter_size_x < 3 {
             filter_size_x = 3;
         }
@@ -261,12 +191,8 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -290,23 +216,19 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
                     Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -319,7 +241,7 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -350,7 +272,7 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                             max_val = f64::NEG_INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input_fn(row2, col + midpoint_x);
-                                if z_n != nodata {
+                                if !is_nodata(z_n, nodata) {
                                     if z_n < min_val {
                                         min_val = z_n;
                                     }
@@ -370,7 +292,7 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input_fn(row2, col2);
-                                    if z_n != nodata {
+                                    if !is_nodata(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -384,7 +306,7 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                             }
                         }
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             min_val = f64::INFINITY;
                             max_val = f64::NEG_INFINITY;
                             min_val2 = min_val;
@@ -418,7 +340,9 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
                             }
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -426,10 +350,11 @@ impl WhiteboxTool for ConservativeSmoothingFilter {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,518 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Shared convolution engine used by the MeanFilter, GaussianFilter, LaplacianFilter,
+SobelFilter, and UserDefinedWeightsFilter tools. Rather than visiting the neighbourhood of each
+cell in turn, the engine accumulates whole rows of weighted values at a time, which allows the
+inner loop to use SIMD instructions (AVX, when it is detected at runtime on x86-64 CPUs).
+Separable kernels are applied as a horizontal pass followed by a vertical pass, and uniform
+(box) factors are evaluated with running sums, making their cost independent of kernel size.
+When the --compute=gpu flag is used, the convolution is performed on the GPU instead, if possible.
+*/
+
+use crate::tools::gpu::{self, FocalGrid, FocalKernel};
+use crate::tools::ProgressReporter;
+use whitebox_raster::{is_nodata, Raster};
+use std::io::Error;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// How NoData neighbours, including those beyond the edges of the grid, are treated.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum NodataMode {
+    /// NoData neighbours are skipped and the weighted sum is divided by the sum of the weights of
+    /// the valid neighbours. Cells for which this sum is not positive are assigned NoData.
+    Normalize,
+    /// NoData neighbours are skipped.
+    Ignore,
+    /// NoData neighbours take the value of the filtered (centre) cell.
+    ReplaceWithCentre,
+}
+
+/// A convolution kernel, with weights stored in row-major order.
+pub(super) struct Kernel {
+    rows: usize,
+    columns: usize,
+    /// The kernel row and column aligned with the filtered cell.
+    center_row: isize,
+    center_col: isize,
+    weights: Vec<f64>,
+    /// The vertical and horizontal factors of the kernel, when it is worth applying separably.
+    factors: Option<(Vec<f64>, Vec<f64>)>,
+}
+
+impl Kernel {
+    /// Creates a kernel from `weights` in row-major order. Separable kernels are detected
+    /// automatically.
+    pub fn new(
+        weights: Vec<f64>,
+        rows: usize,
+        columns: usize,
+        center_row: isize,
+        center_col: isize,
+    ) -> Kernel {
+        let factors = separate(&weights, rows, columns);
+        Kernel {
+            rows: rows,
+            columns: columns,
+            center_row: center_row,
+            center_col: center_col,
+            weights: weights,
+            factors: factors,
+        }
+    }
+
+    /// Creates a centred, separable kernel that is the outer product of the `vertical` and
+    /// `horizontal` factors, each of which should have an odd length.
+    pub fn separable(vertical: Vec<f64>, horizontal: Vec<f64>) -> Kernel {
+        let rows = vertical.len();
+        let columns = horizontal.len();
+        let mut weights = Vec::with_capacity(rows * columns);
+        for v in &vertical {
+            for h in &horizontal {
+                weights.push(v * h);
+            }
+        }
+        Kernel {
+            rows: rows,
+            columns: columns,
+            center_row: (rows / 2) as isize,
+            center_col: (columns / 2) as isize,
+            weights: weights,
+            factors: Some((vertical, horizontal)),
+        }
+    }
+}
+
+/// Returns the vertical and horizontal factors of a rank-one kernel, provided that applying them
+/// separately requires fewer operations than applying the kernel directly.
+fn separate(weights: &[f64], rows: usize, columns: usize) -> Option<(Vec<f64>, Vec<f64>)> {
+    let num_nonzero = weights.iter().filter(|w| **w != 0f64).count();
+    if rows + columns >= num_nonzero {
+        return None;
+    }
+    // pivot on the largest weight
+    let (mut pivot_row, mut pivot_col, mut max_weight) = (0usize, 0usize, 0f64);
+    for r in 0..rows {
+        for c in 0..columns {
+            if weights[r * columns + c].abs() > max_weight {
+                max_weight = weights[r * columns + c].abs();
+                pivot_row = r;
+                pivot_col = c;
+            }
+        }
+    }
+    let pivot = weights[pivot_row * columns + pivot_col];
+    let vertical: Vec<f64> = (0..rows).map(|r| weights[r * columns + pivot_col]).collect();
+    let horizontal: Vec<f64> = (0..columns)
+        .map(|c| weights[pivot_row * columns + c] / pivot)
+        .collect();
+    for r in 0..rows {
+        for c in 0..columns {
+            if (weights[r * columns + c] - vertical[r] * horizontal[c]).abs() > 1e-12 * max_weight {
+                return None;
+            }
+        }
+    }
+    Some((vertical, horizontal))
+}
+
+/// A raster prepared for convolution, in which NoData cells hold zero, along with a grid holding
+/// one for valid cells and zero for NoData cells.
+pub(super) struct ConvolutionInput {
+    rows: isize,
+    columns: isize,
+    values: Vec<f64>,
+    valid: Vec<f64>,
+}
+
+impl ConvolutionInput {
+    /// Reads `input`, applying `transform`, if provided, to each valid value, e.g. to extract the
+    /// intensity of RGB images.
+    pub fn new(input: &Raster, transform: Option<fn(f64) -> f64>) -> ConvolutionInput {
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let mut values = Vec::with_capacity((rows * columns) as usize);
+        let mut valid = Vec::with_capacity((rows * columns) as usize);
+        for row in 0..rows {
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if !is_nodata(z, nodata) {
+                    values.push(match transform {
+                        Some(f) => f(z),
+                        None => z,
+                    });
+                    valid.push(1f64);
+                } else {
+                    values.push(0f64);
+                    valid.push(0f64);
+                }
+            }
+        }
+        ConvolutionInput {
+            rows: rows,
+            columns: columns,
+            values: values,
+            valid: valid,
+        }
+    }
+
+    fn row(&self, row: isize) -> (&[f64], &[f64]) {
+        let start = (row * self.columns) as usize;
+        let end = start + self.columns as usize;
+        (&self.values[start..end], &self.valid[start..end])
+    }
+}
+
+impl FocalGrid for ConvolutionInput {
+    fn rows(&self) -> usize {
+        self.rows as usize
+    }
+
+    fn columns(&self) -> usize {
+        self.columns as usize
+    }
+
+    fn value(&self, row: isize, column: isize) -> Option<f64> {
+        if row < 0 || row >= self.rows || column < 0 || column >= self.columns {
+            return None;
+        }
+        let i = (row * self.columns + column) as usize;
+        if self.valid[i] != 0f64 {
+            Some(self.values[i])
+        } else {
+            None
+        }
+    }
+}
+
+/// Convolves `input` with `kernel` using `num_procs` threads, or the GPU if it is requested and
+/// available, and returns the output rows. Cells that are NoData in the input are assigned
+/// `nodata` in the output.
+pub(super) fn convolve(
+    input: &Arc<ConvolutionInput>,
+    kernel: &Arc<Kernel>,
+    mode: NodataMode,
+    nodata: f64,
+    num_procs: isize,
+    verbose: bool,
+    reporter: &dyn ProgressReporter,
+) -> Result<Vec<Vec<f64>>, Error> {
+    if gpu::gpu_requested() {
+        let gpu_kernel = FocalKernel::Convolution {
+            weights: &kernel.weights,
+            rows: kernel.rows,
+            columns: kernel.columns,
+            center_row: kernel.center_row as usize,
+            center_col: kernel.center_col as usize,
+            mode: match mode {
+                NodataMode::Normalize => 0,
+                NodataMode::Ignore => 1,
+                NodataMode::ReplaceWithCentre => 2,
+            },
+        };
+        if let Some(output) = gpu::run_focal_grid(&**input, &gpu_kernel, nodata, verbose) {
+            reporter.check_cancelled()?;
+            return Ok(output);
+        }
+    }
+
+    let rows = input.rows;
+    let num_procs = num_procs.max(1).min(rows.max(1));
+    // Each thread filters a contiguous block of rows, so that the horizontal pass of a separable
+    // kernel is shared by the rows of the block.
+    let block_size = (rows + num_procs - 1) / num_procs;
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let kernel = kernel.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            let start_row = tid * block_size;
+            let end_row = (start_row + block_size).min(rows);
+            if start_row < end_row {
+                filter_block(&input, &kernel, mode, nodata, start_row, end_row, &tx);
+            }
+        });
+    }
+    drop(tx);
+
+    let mut output: Vec<Vec<f64>> = vec![vec![]; rows as usize];
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+    for row in 0..rows {
+        let (r, data) = rx.recv().expect("Error receiving data from thread.");
+        output[r as usize] = data;
+        reporter.check_cancelled()?;
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Progress", progress);
+                old_progress = progress;
+            }
+        }
+    }
+    Ok(output)
+}
+
+fn filter_block(
+    input: &ConvolutionInput,
+    kernel: &Kernel,
+    mode: NodataMode,
+    nodata: f64,
+    start_row: isize,
+    end_row: isize,
+    tx: &mpsc::Sender<(isize, Vec<f64>)>,
+) {
+    let columns = input.columns as usize;
+    let need_weights = mode != NodataMode::Ignore;
+    let total_weight: f64 = kernel.weights.iter().sum();
+    let mut sum_values = vec![0f64; columns];
+    let mut sum_weights = vec![0f64; columns];
+
+    let finish_row = |row: isize, sum_values: &[f64], sum_weights: &[f64]| {
+        let (values, valid) = input.row(row);
+        let mut data = vec![nodata; columns];
+        for col in 0..columns {
+            if valid[col] == 0f64 {
+                continue;
+            }
+            data[col] = match mode {
+                NodataMode::Ignore => sum_values[col],
+                NodataMode::Normalize => {
+                    if sum_weights[col] > 0f64 {
+                        sum_values[col] / sum_weights[col]
+                    } else {
+                        nodata
+                    }
+                }
+                NodataMode::ReplaceWithCentre => {
+                    sum_values[col] + values[col] * (total_weight - sum_weights[col])
+                }
+            };
+        }
+        if tx.send((row, data)).is_err() {
+            return;
+        }
+    };
+
+    match &kernel.factors {
+        Some((vertical, horizontal)) => {
+            // horizontal pass over each input row contributing to the block
+            let first_row = (start_row - kernel.center_row).max(0);
+            let last_row =
+                (end_row - 1 + kernel.rows as isize - 1 - kernel.center_row).min(input.rows - 1);
+            let num_rows = (last_row - first_row + 1).max(0) as usize;
+            let mut h_values = vec![0f64; num_rows * columns];
+            let mut h_weights = vec![0f64; if need_weights { num_rows * columns } else { 0 }];
+            for i in 0..num_rows {
+                let (values, valid) = input.row(first_row + i as isize);
+                let range = i * columns..(i + 1) * columns;
+                filter_1d(values, horizontal, kernel.center_col, &mut h_values[range.clone()]);
+                if need_weights {
+                    filter_1d(valid, horizontal, kernel.center_col, &mut h_weights[range]);
+                }
+            }
+            let h_row = |data: &[f64], row: isize| -> Option<std::ops::Range<usize>> {
+                if row < first_row || row > last_row || data.is_empty() {
+                    return None;
+                }
+                let i = (row - first_row) as usize;
+                Some(i * columns..(i + 1) * columns)
+            };
+
+            // vertical pass
+            if let Some(w) = uniform_weight(vertical) {
+                // running sums of the rows within the window
+                let mut running_values = vec![0f64; columns];
+                let mut running_weights = vec![0f64; columns];
+                for row in start_row..end_row {
+                    if row == start_row {
+                        for k in 0..vertical.len() as isize {
+                            let r = row + k - kernel.center_row;
+                            if let Some(range) = h_row(&h_values, r) {
+                                axpy(&mut running_values, 1f64, &h_values[range.clone()]);
+                                if need_weights {
+                                    axpy(&mut running_weights, 1f64, &h_weights[range]);
+                                }
+                            }
+                        }
+                    } else {
+                        let entering = row + vertical.len() as isize - 1 - kernel.center_row;
+                        let leaving = row - 1 - kernel.center_row;
+                        if let Some(range) = h_row(&h_values, entering) {
+                            axpy(&mut running_values, 1f64, &h_values[range.clone()]);
+                            if need_weights {
+                                axpy(&mut running_weights, 1f64, &h_weights[range]);
+                            }
+                        }
+                        if let Some(range) = h_row(&h_values, leaving) {
+                            axpy(&mut running_values, -1f64, &h_values[range.clone()]);
+                            if need_weights {
+                                axpy(&mut running_weights, -1f64, &h_weights[range]);
+                            }
+                        }
+                    }
+                    for col in 0..columns {
+                        sum_values[col] = w * running_values[col];
+                        sum_weights[col] = w * running_weights[col];
+                    }
+                    finish_row(row, &sum_values, &sum_weights);
+                }
+            } else {
+                for row in start_row..end_row {
+                    sum_values.iter_mut().for_each(|v| *v = 0f64);
+                    sum_weights.iter_mut().for_each(|v| *v = 0f64);
+                    for (k, w) in vertical.iter().enumerate() {
+                        if *w == 0f64 {
+                            continue;
+                        }
+                        let r = row + k as isize - kernel.center_row;
+                        if let Some(range) = h_row(&h_values, r) {
+                            axpy(&mut sum_values, *w, &h_values[range.clone()]);
+                            if need_weights {
+                                axpy(&mut sum_weights, *w, &h_weights[range]);
+                            }
+                        }
+                    }
+                    finish_row(row, &sum_values, &sum_weights);
+                }
+            }
+        }
+        None => {
+            for row in start_row..end_row {
+                sum_values.iter_mut().for_each(|v| *v = 0f64);
+                sum_weights.iter_mut().for_each(|v| *v = 0f64);
+                for kr in 0..kernel.rows {
+                    let r = row + kr as isize - kernel.center_row;
+                    if r < 0 || r >= input.rows {
+                        continue;
+                    }
+                    let (values, valid) = input.row(r);
+                    for kc in 0..kernel.columns {
+                        let w = kernel.weights[kr * kernel.columns + kc];
+                        if w == 0f64 {
+                            continue;
+                        }
+                        let offset = kc as isize - kernel.center_col;
+                        if let Some((out, src)) = overlap(columns, offset) {
+                            axpy(&mut sum_values[out.clone()], w, &values[src.clone()]);
+                            if need_weights {
+                                axpy(&mut sum_weights[out], w, &valid[src]);
+                            }
+                        }
+                    }
+                }
+                finish_row(row, &sum_values, &sum_weights);
+            }
+        }
+    }
+}
+
+/// Applies the one-dimensional `weights`, whose element `center` is aligned with each output
+/// cell, along `src`, treating values beyond its ends as zero.
+fn filter_1d(src: &[f64], weights: &[f64], center: isize, out: &mut [f64]) {
+    let n = src.len() as isize;
+    out.iter_mut().for_each(|v| *v = 0f64);
+    if let Some(w) = uniform_weight(weights) {
+        // running sum over the window [col - center, col - center + len - 1]
+        let len = weights.len() as isize;
+        let mut sum = 0f64;
+        for i in -center..len - center {
+            if i >= 0 && i < n {
+                sum += src[i as usize];
+            }
+        }
+        out[0] = w * sum;
+        for col in 1..n {
+            let entering = col - center + len - 1;
+            let leaving = col - center - 1;
+            if entering >= 0 && entering < n {
+                sum += src[entering as usize];
+            }
+            if leaving >= 0 && leaving < n {
+                sum -= src[leaving as usize];
+            }
+            out[col as usize] = w * sum;
+        }
+    } else {
+        for (k, w) in weights.iter().enumerate() {
+            if *w == 0f64 {
+                continue;
+            }
+            if let Some((o, s)) = overlap(src.len(), k as isize - center) {
+                axpy(&mut out[o], *w, &src[s]);
+            }
+        }
+    }
+}
+
+/// Returns the common weight of a kernel factor in which all weights are equal, if it is long
+/// enough for running sums to be worthwhile.
+fn uniform_weight(weights: &[f64]) -> Option<f64> {
+    if weights.len() > 3 && weights.iter().all(|w| *w == weights[0]) {
+        Some(weights[0])
+    } else {
+        None
+    }
+}
+
+/// Returns the ranges of output and source cells within a row of `n` cells that overlap when the
+/// source is shifted by `offset` cells.
+fn overlap(
+    n: usize,
+    offset: isize,
+) -> Option<(std::ops::Range<usize>, std::ops::Range<usize>)> {
+    let n = n as isize;
+    let start = (-offset).max(0);
+    let end = (n - offset).min(n);
+    if start >= end {
+        return None;
+    }
+    Some((
+        start as usize..end as usize,
+        (start + offset) as usize..(end + offset) as usize,
+    ))
+}
+
+/// Computes `acc[i] += w * x[i]`, using AVX instructions when they are available.
+fn axpy(acc: &mut [f64], w: f64, x: &[f64]) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx") {
+            // Safety: the AVX instruction set has been detected on the running CPU.
+            unsafe {
+                axpy_avx(acc, w, x);
+            }
+            return;
+        }
+    }
+    for (a, v) in acc.iter_mut().zip(x) {
+        *a += w * v;
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn axpy_avx(acc: &mut [f64], w: f64, x: &[f64]) {
+    use std::arch::x86_64::*;
+    let n = acc.len().min(x.len());
+    let weight = _mm256_set1_pd(w);
+    let mut i = 0;
+    while i + 4 <= n {
+        let a = _mm256_loadu_pd(acc.as_ptr().add(i));
+        let v = _mm256_loadu_pd(x.as_ptr().add(i));
+        _mm256_storeu_pd(acc.as_mut_ptr().add(i), _mm256_add_pd(a, _mm256_mul_pd(weight, v)));
+        i += 4;
+    }
+    while i < n {
+        acc[i] += w * x[i];
+        i += 1;
+    }
+}
@@ -10,7 +10,6 @@ use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
@@ -18,7 +17,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to reduce vignetting within an image. Vignetting refers to the
 /// reduction of image brightness away from the image centre (i.e. the principal point).
@@ -167,86 +166,15 @@ impl WhiteboxTool for CorrectVignetting {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut pp_file = String::new();
-        let mut output_file = String::new();
-        let mut focal_length = 304.8f64;
-        let mut image_width = 228.6f64;
-        let mut n_param = 4f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-pp" {
-                pp_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-focal_length" {
-                focal_length = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-image_width" {
-                image_width = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-n" {
-                n_param = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut pp_file = parsed.get_string("pp").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let focal_length = parsed.get_f64("focal_length").unwrap_or(304.8f64);
+        let image_width = parsed.get_f64("image_width").unwrap_or(228.6f64);
+        let n_param = parsed.get_f64("n").unwrap_or(4f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -259,20 +187,12 @@ impl WhiteboxTool for CorrectVignetting {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !pp_file.contains(&sep) && !pp_file.contains("/") {
-            pp_file = format!("{}{}", working_directory, pp_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        pp_file = resolve_input_path(&pp_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading input data...")
@@ -315,23 +235,19 @@ impl WhiteboxTool for CorrectVignetting {
         let pp_x = input.get_column_from_x(vector_data.get_record(0).points[0].x) as f64;
         let pp_y = input.get_row_from_y(vector_data.get_record(0).points[0].y) as f64;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
                     Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -349,7 +265,7 @@ impl WhiteboxTool for CorrectVignetting {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z_in = input_fn(row, col);
-                        if z_in != nodata {
+                        if !is_nodata(z_in, nodata) {
                             dist = ((row as f64 - pp_y) * (row as f64 - pp_y)
                                 + (col as f64 - pp_x) * (col as f64 - pp_x))
                                 .sqrt();
@@ -370,8 +286,9 @@ impl WhiteboxTool for CorrectVignetting {
                             }
                         }
                     }
-                    tx.send((row, data, min_in, max_in, min_out, max_out))
-                        .unwrap();
+                    if tx.send((row, data, min_in, max_in, min_out, max_out)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -396,10 +313,11 @@ impl WhiteboxTool for CorrectVignetting {
                 max_out = d;
             }
             unscaled_data.set_row_data(row, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -413,14 +331,14 @@ impl WhiteboxTool for CorrectVignetting {
             let input = input.clone();
             let unscaled_data = unscaled_data.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
                     // simply return the value.
                     Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, value);
                             return ret;
@@ -434,12 +352,14 @@ impl WhiteboxTool for CorrectVignetting {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z_in = unscaled_data.get_value(row, col);
-                        if z_in != nodata {
+                        if !is_nodata(z_in, nodata) {
                             z_out = min_in + (z_in - min_out) / range_out * range_in;
                             data[col as usize] = output_fn(row, col, z_out);
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -452,10 +372,11 @@ impl WhiteboxTool for CorrectVignetting {
         for r in 0..rows {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to create a colour-composite image from three bands of multi-spectral imagery.
 /// The user must specify the names of the input images to enter into the red, green, and blue channels
@@ -109,7 +108,7 @@ impl CreateColourComposite {
                 "Optional flag indicating whether a balance contrast enhancement is performed."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true,
         });
 
@@ -181,72 +180,20 @@ impl WhiteboxTool for CreateColourComposite {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input1_file = String::new();
-        let mut input2_file = String::new();
-        let mut input3_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input1_file = parsed.get_string("red").unwrap_or_default();
+        let mut input2_file = parsed.get_string("green").unwrap_or_default();
+        let mut input3_file = parsed.get_string("blue").unwrap_or_default();
         let mut input4_file = String::new();
         let mut input4_used = false;
-        let mut output_file = String::new();
-        let mut enhance = false;
-        let mut no_zeros = false;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-red" {
-                input1_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-green" {
-                input2_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-blue" {
-                input3_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-opacity" {
-                input4_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                input4_used = true;
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-enhance" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    enhance = true;
-                }
-            } else if flag_val == "-zeros" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    // treat zero values as nodata.
-                    no_zeros = true;
-                }
-            }
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let enhance = parsed.get_bool("enhance");
+        let no_zeros = parsed.get_bool("zeros");
+        if let Some(value) = parsed.get_string("opacity") {
+            input4_file = value;
+            input4_used = true;
         }
 
         if verbose {
@@ -260,28 +207,16 @@ impl WhiteboxTool for CreateColourComposite {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input1_file.contains(&sep) && !input1_file.contains("/") {
-            input1_file = format!("{}{}", working_directory, input1_file);
-        }
-        if !input2_file.contains(&sep) && !input2_file.contains("/") {
-            input2_file = format!("{}{}", working_directory, input2_file);
-        }
-        if !input3_file.contains(&sep) && !input3_file.contains("/") {
-            input3_file = format!("{}{}", working_directory, input3_file);
-        }
+        input1_file = resolve_input_path(&input1_file, working_directory)?;
+        input2_file = resolve_input_path(&input2_file, working_directory)?;
+        input3_file = resolve_input_path(&input3_file, working_directory)?;
         if input4_used {
-            if !input4_file.contains(&sep) && !input4_file.contains("/") {
-                input4_file = format!("{}{}", working_directory, input4_file);
-            }
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
+            input4_file = resolve_input_path(&input4_file, working_directory)?;
         }
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading red band data...")
@@ -325,9 +260,9 @@ impl WhiteboxTool for CreateColourComposite {
                 if input_r.configs.rows != opacity.configs.rows
                     || input_r.configs.columns != opacity.configs.columns
                 {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                                          "The input files must have the same number of rows and columns and spatial extent."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
                 }
+                warn_if_crs_differs(&input_r, &opacity, verbose);
                 Arc::new(opacity.get_data_as_array2d())
             }
             false => {
@@ -344,26 +279,18 @@ impl WhiteboxTool for CreateColourComposite {
         if input_r.configs.rows != input_g.configs.rows
             || input_r.configs.columns != input_g.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input_r, &input_g, verbose);
         if input_r.configs.rows != input_b.configs.rows
             || input_r.configs.columns != input_b.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input_r, &input_b, verbose);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input_r = input_r.clone();
@@ -371,7 +298,7 @@ impl WhiteboxTool for CreateColourComposite {
             let input_b = input_b.clone();
             let input_a = input_a.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut red_val: f64;
                 let mut green_val: f64;
                 let mut blue_val: f64;
@@ -383,7 +310,7 @@ impl WhiteboxTool for CreateColourComposite {
                         red_val = input_r[(row, col)];
                         green_val = input_g[(row, col)];
                         blue_val = input_b[(row, col)];
-                        if red_val != nodata_r && green_val != nodata_g && blue_val != nodata_b {
+                        if !is_nodata(red_val, nodata_r) && !is_nodata(green_val, nodata_g) && !is_nodata(blue_val, nodata_b) {
                             red_val = (red_val - red_min) / red_range * 255f64;
                             if red_val < 0f64 {
                                 red_val = 0f64;
@@ -423,7 +350,9 @@ impl WhiteboxTool for CreateColourComposite {
                             data[col as usize] = ((a << 24) | (b << 16) | (g << 8) | r) as f64;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -434,10 +363,11 @@ impl WhiteboxTool for CreateColourComposite {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -468,7 +398,7 @@ impl WhiteboxTool for CreateColourComposite {
             for row in 0..rows {
                 for col in 0..columns {
                     z = output[(row, col)];
-                    if z != nodata_r {
+                    if !is_nodata(z, nodata_r) {
                         num_pixels += 1f64;
                         r = z as u32 & 0xFF;
                         g = (z as u32 >> 8) & 0xFF;
@@ -502,10 +432,11 @@ impl WhiteboxTool for CreateColourComposite {
                         b_sqr_total += (b * b) as f64;
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Performing Enhancement (1 of 2): {}%", progress);
+                        reporter.progress("Performing Enhancement (1 of 2)", progress);
                         old_progress = progress;
                     }
                 }
@@ -537,7 +468,7 @@ impl WhiteboxTool for CreateColourComposite {
             for row in 0..rows {
                 for col in 0..columns {
                     z = output[(row, col)];
-                    if z != nodata_r {
+                    if !is_nodata(z, nodata_r) {
                         r = z as u32 & 0xFF;
                         g = (z as u32 >> 8) & 0xFF;
                         b = (z as u32 >> 16) & 0xFF;
@@ -575,10 +506,11 @@ impl WhiteboxTool for CreateColourComposite {
                             ((a << 24) | (b_out << 16) | (g_out << 8) | r_out) as f64
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Performing Enhancement (2 of 2): {}%", progress);
+                        reporter.progress("Performing Enhancement (2 of 2)", progress);
                         old_progress = progress;
                     }
                 }
@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// The Direct Decorrelation Stretch (DDS) is a simple type of saturation stretch. The stretch is
 /// applied to a colour composite image and is used to improve the saturation, or colourfulness,
@@ -86,7 +85,7 @@ impl DirectDecorrelationStretch {
 
         parameters.push(ToolParameter {
             name: "Percent to clip the upper tail".to_owned(),
-            flags: vec!["--clip".to_owned()],
+            flags: vec!["--clip_percent".to_owned(), "--clip".to_owned()],
             description: "Optional percent to clip the upper tail by during the stretch."
                 .to_owned(),
             parameter_type: ParameterType::Float,
@@ -156,71 +155,22 @@ impl WhiteboxTool for DirectDecorrelationStretch {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut achromatic_factor = 0.5f64;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut achromatic_factor = parsed.get_f64("k").unwrap_or(0.5f64);
         let mut clip_percent = 0.01f64;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_f64("clip") {
+            clip_percent = value;
+            if clip_percent < 0f64 {
+                clip_percent = 0f64;
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-k" {
-                if keyval {
-                    achromatic_factor = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    achromatic_factor = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-clip_percent" || flag_val == "-clip" {
-                if keyval {
-                    clip_percent = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    clip_percent = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-                if clip_percent < 0f64 {
-                    clip_percent = 0f64;
-                }
-                if clip_percent > 50f64 {
-                    clip_percent = 50f64;
-                }
-                clip_percent = clip_percent / 100f64;
+            if clip_percent > 50f64 {
+                clip_percent = 50f64;
             }
+            clip_percent = clip_percent / 100f64;
         }
 
         if verbose {
@@ -234,8 +184,6 @@ impl WhiteboxTool for DirectDecorrelationStretch {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -246,12 +194,8 @@ impl WhiteboxTool for DirectDecorrelationStretch {
             achromatic_factor = 1f64;
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading image data...")
@@ -266,17 +210,13 @@ impl WhiteboxTool for DirectDecorrelationStretch {
         let nodata = input.configs.nodata;
         let rgb_nodata = 0f64;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let (mut red, mut green, mut blue): (u32, u32, u32);
                 let (mut r_out, mut g_out, mut b_out): (f64, f64, f64);
@@ -291,7 +231,7 @@ impl WhiteboxTool for DirectDecorrelationStretch {
                     let mut num_cells = 0;
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             red = z as u32 & 0xFF;
                             green = (z as u32 >> 8) & 0xFF;
                             blue = (z as u32 >> 16) & 0xFF;
@@ -338,7 +278,7 @@ impl WhiteboxTool for DirectDecorrelationStretch {
                             num_cells += 1;
                         }
                     }
-                    tx.send((
+                    if tx.send((
                         row,
                         data_r,
                         histo_red,
@@ -347,8 +287,9 @@ impl WhiteboxTool for DirectDecorrelationStretch {
                         data_b,
                         histo_blue,
                         num_cells,
-                    ))
-                    .unwrap();
+                    )).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -378,10 +319,11 @@ impl WhiteboxTool for DirectDecorrelationStretch {
             }
 
             num_cells += data.7;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -469,14 +411,14 @@ impl WhiteboxTool for DirectDecorrelationStretch {
             let green_band = green_band.clone();
             let blue_band = blue_band.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let (mut red, mut green, mut blue, mut a): (u32, u32, u32, u32);
                 for row in (0..rows).filter(|row_val| row_val % num_procs == tid) {
                     let mut data = vec![rgb_nodata; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             red = red_band[(row, col)] as u32;
                             if red < stretch_min as u32 {
                                 red = stretch_min as u32;
@@ -511,7 +453,9 @@ impl WhiteboxTool for DirectDecorrelationStretch {
                                 ((a << 24) | (blue << 16) | (green << 8) | red) as f64;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -523,10 +467,11 @@ impl WhiteboxTool for DirectDecorrelationStretch {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
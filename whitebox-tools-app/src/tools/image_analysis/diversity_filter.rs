@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool assigns each cell in the output grid the number of different values in a moving window centred on
 /// each grid cell in the input raster. The input image should contain integer values but floating point data
@@ -140,77 +139,16 @@ impl WhiteboxTool for DiversityFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size_x = 11usize;
-        let mut filter_size_y = 11usize;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-filter" {
-                filter_size_x = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-                filter_size_y = filter_size_x;
-            } else if flag_val == "-filterx" {
-                filter_size_x = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-filtery" {
-                filter_size_y = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size_x = parsed.get_usize("filterx")?.unwrap_or(11usize);
+        let mut filter_size_y = parsed.get_usize("filtery")?.unwrap_or(11usize);
+        if let Some(value) = parsed.get_silent_f64("filter")? {
+            filter_size_x = value as usize;
+            filter_size_y = filter_size_x;
         }
 
         if verbose {
@@ -224,8 +162,6 @@ impl WhiteboxTool for DiversityFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         if filter_size_x < 3 {
             filter_size_x = 3;
         }
@@ -247,12 +183,8 @@ impl WhiteboxTool for DiversityFilter {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -280,17 +212,13 @@ impl WhiteboxTool for DiversityFilter {
         let min_val_mult = min_val * multiplier;
         let num_bins = (max_val * multiplier - min_val_mult).ceil() as usize + 1;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut bin_val: usize;
                 let (mut start_col, mut end_col, mut start_row, mut end_row): (
                     isize,
@@ -313,7 +241,7 @@ impl WhiteboxTool for DiversityFilter {
                             // remove the trailing column from the histo
                             for row2 in start_row..=end_row {
                                 z = input.get_value(row2, start_col - 1);
-                                if z != nodata {
+                                if !is_nodata(z, nodata) {
                                     bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                     histo[bin_val] -= 1;
                                     if histo[bin_val] == 0 {
@@ -325,7 +253,7 @@ impl WhiteboxTool for DiversityFilter {
                             // add the leading column to the histo
                             for row2 in start_row..=end_row {
                                 z = input.get_value(row2, end_col);
-                                if z != nodata {
+                                if !is_nodata(z, nodata) {
                                     bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                     if histo[bin_val] == 0 {
                                         diversity += 1;
@@ -340,7 +268,7 @@ impl WhiteboxTool for DiversityFilter {
                             for col2 in start_col..=end_col {
                                 for row2 in start_row..=end_row {
                                     z = input.get_value(row2, col2);
-                                    if z != nodata {
+                                    if !is_nodata(z, nodata) {
                                         bin_val = (z * multiplier - min_val_mult).floor() as usize;
                                         if histo[bin_val] == 0 {
                                             diversity += 1;
@@ -350,11 +278,13 @@ impl WhiteboxTool for DiversityFilter {
                                 }
                             }
                         }
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata(input.get_value(row, col), nodata) {
                             data[col as usize] = diversity as f64;
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -362,10 +292,11 @@ impl WhiteboxTool for DiversityFilter {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -8,7 +8,6 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
@@ -16,7 +15,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to perform a difference-of-Gaussians (DoG) filter on a raster image. In digital
 /// image processing, DoG is a feature enhancement algorithm that involves the subtraction of one blurred
@@ -154,67 +153,15 @@ impl WhiteboxTool for DiffOfGaussianFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut filter_size1 = 0usize;
         let mut filter_size2 = 0usize;
-        let mut sigma1 = 2.0;
-        let mut sigma2 = 4.0;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-sigma1" {
-                if keyval {
-                    sigma1 = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    sigma1 = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-sigma2" {
-                if keyval {
-                    sigma2 = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    sigma2 = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let mut sigma1 = parsed.get_f64("sigma1").unwrap_or(2.0);
+        let mut sigma2 = parsed.get_f64("sigma2").unwrap_or(4.0);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -227,14 +174,8 @@ impl WhiteboxTool for DiffOfGaussianFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if sigma1 < 0.25 {
             println!("sigma1 cannot be less than 0.25. The value has been modified.");
@@ -390,12 +331,8 @@ impl WhiteboxTool for DiffOfGaussianFilter {
         output.configs.data_type = DataType::F32;
         output.configs.photometric_interp = PhotometricInterpretation::Continuous;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
@@ -406,13 +343,13 @@ impl WhiteboxTool for DiffOfGaussianFilter {
             let d_y2 = d_y2.clone();
             let weights2 = weights2.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
                     Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -427,14 +364,14 @@ impl WhiteboxTool for DiffOfGaussianFilter {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             sum = 0.0;
                             z_final1 = 0.0;
                             for a in 0..num_pixels_in_filter1 {
                                 x = col + d_x1[a];
                                 y = row + d_y1[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata(zn, nodata) {
                                     sum += weights1[a];
                                     z_final1 += weights1[a] * zn;
                                 }
@@ -447,7 +384,7 @@ impl WhiteboxTool for DiffOfGaussianFilter {
                                 x = col + d_x2[a];
                                 y = row + d_y2[a];
                                 zn = input_fn(y, x);
-                                if zn != nodata {
+                                if !is_nodata(zn, nodata) {
                                     sum += weights2[a];
                                     z_final2 += weights2[a] * zn;
                                 }
@@ -458,7 +395,9 @@ impl WhiteboxTool for DiffOfGaussianFilter {
                         }
                     }
 
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -466,10 +405,11 @@ impl WhiteboxTool for DiffOfGaussianFilter {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
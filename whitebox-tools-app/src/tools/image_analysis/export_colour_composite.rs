@@ -0,0 +1,413 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::rendering::*;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool creates a colour composite image from three bands of a multispectral image, e.g. a
+/// true-colour composite from the red, green and blue bands or a false-colour composite from the
+/// near-infrared, red and green bands, as a visual product that can be shared and viewed without GIS
+/// software. The output (`--output`) is written as a PNG image if its file name has the extension .png,
+/// with a world file (.pgw) and, if the red band's coordinate reference system is known, a .prj file
+/// that georeference it, or otherwise as a 32-bit RGBA colour raster (e.g. a GeoTIFF). Cells that are
+/// NoData in any band are transparent.
+///
+/// Each band is contrast stretched independently, by scaling a range of its values to the full range
+/// of intensities (0-255). The range is found using one of the stretches (`--stretch`):
+///
+/// - `none`: the values are intensities, i.e. the range is 0-255.
+/// - `minmax`: the range of the band's values.
+/// - `percent` (the default): the range of values after clipping the percentage `--clip` (by default,
+///   2%) of the cells from each tail of the band's distribution.
+/// - `stdev`: `--num_stdev` (by default, two) standard deviations either side of the band's mean.
+///
+/// Alternatively, the ranges of the red, green and blue bands may be given explicitly by the lists
+/// `--min_values` and `--max_values`, e.g. `--min_values='250,400,300' --max_values='1800,1600,1400'`,
+/// which keeps the colours of composites created from several images consistent. A gamma correction
+/// (`--gamma`) may be applied to the stretched intensities; gamma values greater than one brighten the
+/// mid-tones, and values less than one darken them.
+///
+/// The green and blue bands must share the grid of the red band, unless `--align=auto` is specified, in
+/// which case they are resampled to it. Unlike the `CreateColourComposite` tool, which applies a single
+/// balance contrast enhancement to the composite, this tool lets the stretch of each band be chosen.
+///
+/// # See Also
+/// `ColourizeRaster`, `CreateColourComposite`, `PercentageContrastStretch`, `StandardDeviationContrastStretch`
+pub struct ExportColourComposite {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExportColourComposite {
+    pub fn new() -> ExportColourComposite {
+        // public constructor
+        let name = "ExportColourComposite".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Creates a colour composite image (RGBA raster or PNG) from three bands, with a contrast stretch of each band."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Red Band File".to_owned(),
+            flags: vec!["--red".to_owned()],
+            description: "Input red band image file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Green Band File".to_owned(),
+            flags: vec!["--green".to_owned()],
+            description: "Input green band image file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Blue Band File".to_owned(),
+            flags: vec!["--blue".to_owned()],
+            description: "Input blue band image file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output colour raster or PNG (.png) file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Contrast Stretch".to_owned(),
+            flags: vec!["--stretch".to_owned()],
+            description: "Contrast stretch of each band; one of 'none', 'minmax', 'percent', and 'stdev'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "none".to_owned(),
+                "minmax".to_owned(),
+                "percent".to_owned(),
+                "stdev".to_owned(),
+            ]),
+            default_value: Some("percent".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tail Clip Percent".to_owned(),
+            flags: vec!["--clip".to_owned()],
+            description: "Percentage of cells clipped from each tail of a band's distribution by the percent stretch.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Standard Deviations".to_owned(),
+            flags: vec!["--num_stdev".to_owned()],
+            description: "Number of standard deviations either side of the mean spanned by the stdev stretch.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Values (optional)".to_owned(),
+            flags: vec!["--min_values".to_owned()],
+            description: "Values of the red, green and blue bands stretched to zero intensity.".to_owned(),
+            parameter_type: ParameterType::StringList,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Values (optional)".to_owned(),
+            flags: vec!["--max_values".to_owned()],
+            description: "Values of the red, green and blue bands stretched to full intensity.".to_owned(),
+            parameter_type: ParameterType::StringList,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Gamma".to_owned(),
+            flags: vec!["--gamma".to_owned()],
+            description: "Gamma correction of the stretched intensities.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Alignment".to_owned(),
+            flags: vec!["--align".to_owned()],
+            description: "Input alignment mode; 'auto' resamples the green and blue bands to the grid of the red band.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["none".to_owned(), "auto".to_owned()]),
+            default_value: Some("none".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --red=band4.tif --green=band3.tif --blue=band2.tif -o=true_colour.png --stretch=percent --clip=1.0 --gamma=1.2", short_exe, name).replace("*", &sep);
+
+        ExportColourComposite {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExportColourComposite {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut band_files = vec![
+            parsed.get_string("red").unwrap_or_default(),
+            parsed.get_string("green").unwrap_or_default(),
+            parsed.get_string("blue").unwrap_or_default(),
+        ];
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let stretch = parsed.get_string("stretch").unwrap_or("percent".to_string());
+        let clip = parsed.get_f64("clip").unwrap_or(2f64);
+        let num_stdev = parsed.get_f64("num_stdev").unwrap_or(2f64);
+        let min_values = parse_band_values(&parsed.get_list("min_values"), "--min_values")?;
+        let max_values = parse_band_values(&parsed.get_list("max_values"), "--max_values")?;
+        let gamma = parsed.get_f64("gamma").unwrap_or(1f64);
+        let align = parsed.get_string("align").unwrap_or_default() == "auto";
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if clip < 0f64 || clip >= 50f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The tail clip percent must be between 0 and 50.",
+            ));
+        }
+        if num_stdev <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of standard deviations must be greater than zero.",
+            ));
+        }
+        if gamma <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The gamma value must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let mut bands: Vec<Raster> = Vec::with_capacity(3);
+        for f in band_files.iter_mut() {
+            *f = resolve_input_path(f, working_directory)?;
+            let band = if bands.is_empty() {
+                Raster::new(f, "r")?
+            } else {
+                read_secondary_raster(f, &bands[0], align, true, verbose)?
+            };
+            bands.push(band);
+        }
+        output_file = resolve_path(&output_file, working_directory);
+
+        let start = Instant::now();
+
+        let rows = bands[0].configs.rows as isize;
+        let columns = bands[0].configs.columns as isize;
+
+        // The range of values of each band stretched over the intensities
+        let band_names = ["Red", "Green", "Blue"];
+        let mut ranges = [(0f64, 0f64); 3];
+        for b in 0..3 {
+            let (mut low, mut high) = (0f64, 0f64);
+            if min_values.is_none() || max_values.is_none() {
+                let range = stretch_range(&bands[b], &stretch, clip, num_stdev);
+                low = range.0;
+                high = range.1;
+            }
+            low = min_values.map_or(low, |v| v[b]);
+            high = max_values.map_or(high, |v| v[b]);
+            if low >= high {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "The range of values of the {} band ({} to {}) is empty; specify a different stretch or range of values.",
+                        band_names[b].to_lowercase(),
+                        low,
+                        high
+                    ),
+                ));
+            }
+            if verbose {
+                println!("{} band range: {} to {}", band_names[b], low, high);
+            }
+            ranges[b] = (low, high);
+        }
+
+        let nodata: Vec<f64> = bands.iter().map(|b| b.configs.nodata).collect();
+        let mut pixels: Vec<Rgba> = vec![TRANSPARENT; (rows * columns) as usize];
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for row in 0..rows {
+            for col in 0..columns {
+                let mut pixel = [0u8, 0u8, 0u8, 255u8];
+                let mut any_nodata = false;
+                for b in 0..3 {
+                    let z = bands[b].get_value(row, col);
+                    if is_nodata(z, nodata[b]) {
+                        any_nodata = true;
+                        break;
+                    }
+                    pixel[b] = scale_intensity(z, ranges[b].0, ranges[b].1, gamma);
+                }
+                if !any_nodata {
+                    pixels[(row * columns + col) as usize] = pixel;
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let mut metadata = vec![format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        )];
+        for b in 0..3 {
+            metadata.push(format!("Input {} band file: {}", band_names[b].to_lowercase(), band_files[b]));
+            metadata.push(format!("{} band range: {} to {}", band_names[b], ranges[b].0, ranges[b].1));
+        }
+        metadata.push(format!("Gamma: {}", gamma));
+        metadata.push(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match write_image(&output_file, &bands[0], &pixels, metadata) {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a list of values for the red, green and blue bands, which is either empty or has three
+/// values.
+fn parse_band_values(list: &[String], flag: &str) -> Result<Option<[f64; 3]>, Error> {
+    if list.is_empty() {
+        return Ok(None);
+    }
+    let bad_list = || {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("{} must be a list of three values, for the red, green and blue bands.", flag),
+        )
+    };
+    if list.len() != 3 {
+        return Err(bad_list());
+    }
+    let mut values = [0f64; 3];
+    for i in 0..3 {
+        values[i] = parse_number(&list[i]).ok_or_else(bad_list)?;
+    }
+    Ok(Some(values))
+}
@@ -0,0 +1,669 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use crate::tools::data_tools::VectorPolygonsToRaster;
+use crate::tools::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashMap;
+use std::env;
+use std::f64;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool exports co-registered image chips and label masks from a multiband image and a label
+/// raster or polygon vector, for training deep learning models, e.g. for semantic segmentation. The
+/// image bands (`--inputs`) are provided as a list of single-band rasters. The labels are either a
+/// raster of integer class values (`--labels`) or a polygon vector (`--label_polys`) with a numeric
+/// class field (`--field`), which is rasterized to the grid of the image, with cells outside of the
+/// polygons assigned the background class (`--background`).
+///
+/// Square chips of `--chip_size` grid cells are taken from the image at a spacing of `--stride` cells
+/// (by default, equal to the chip size, i.e. without overlap), starting at the upper-left corner of the
+/// image; chips that would extend beyond the image edges are not exported. A chip is skipped if more
+/// than the fraction `--max_nodata` of its cells are NoData in any band (by default, any NoData cell
+/// excludes a chip), or if fewer than the fraction `--min_labelled` of its cells are labelled with a
+/// class other than the background. Each chip is assigned its *dominant class*, the most frequent
+/// class other than the background (or the background class, if there is no other), and if
+/// `--max_per_class` is specified, at most that many chips are exported for each dominant class, chosen
+/// at random (using `--seed`). Together, these options can be used to balance the number of training
+/// chips among rare and common classes.
+///
+/// Training data may optionally be augmented (`--augment`) by exporting transformed copies of each
+/// chip and its labels: horizontal and vertical flips (`flips`), rotations of 90, 180 and 270 degrees
+/// (`rotations`), or all eight flips and rotations of the square (`all`).
+///
+/// The chips are written to the output directory (`--outdir`) in the NumPy .npy format, which can be
+/// read directly by PyTorch and TensorFlow data pipelines (e.g. using `numpy.load`):
+///
+/// - `images/chip_000001.npy`: a 32-bit float array with the shape (bands, rows, columns), in the order
+///   in which the bands were input; NoData cells are NaN.
+/// - `labels/chip_000001.npy`: a 32-bit integer array with the shape (rows, columns); NoData cells are -1.
+/// - `index.csv`: one record for each exported chip, with the fields CHIP, IMAGE, LABEL (file names
+///   relative to the output directory), ROW and COLUMN (the image grid cell at the upper-left corner of
+///   the chip), X_MIN and Y_MAX (the map coordinates of the chip's upper-left corner), AUGMENTATION,
+///   CLASS (the dominant class), and LABELLED (the fraction of labelled cells).
+///
+/// Augmented copies of a chip have the suffix of their transformation, e.g. `chip_000001_rot90.npy`,
+/// and the same ROW, COLUMN, X_MIN and Y_MAX as the original chip, so that they can be kept in the same
+/// training/validation split. The secondary inputs must share the grid of the first image band, unless
+/// `--align=auto` is specified, in which case they are resampled to it.
+///
+/// # See Also
+/// `VectorPolygonsToRaster`, `KMeansClustering`, `SpectralIndices`
+pub struct ExportImageChips {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExportImageChips {
+    pub fn new() -> ExportImageChips {
+        // public constructor
+        let name = "ExportImageChips".to_string();
+        let toolbox = "Machine Learning".to_string();
+        let description =
+            "Exports image chips and label masks from a multiband image for training deep learning models."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Image Bands".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input image band raster files.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Label Raster File (optional)".to_owned(),
+            flags: vec!["--labels".to_owned()],
+            description: "Input raster of class values; either this or a label polygon file is required.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Label Polygon File (optional)".to_owned(),
+            flags: vec!["--label_polys".to_owned()],
+            description: "Input polygon vector of classes; either this or a label raster is required.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Class Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Numeric class field of the label polygons.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--label_polys".to_string(),
+            ),
+            default_value: Some("CLASS".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Directory".to_owned(),
+            flags: vec!["--outdir".to_owned()],
+            description: "Output directory.".to_owned(),
+            parameter_type: ParameterType::Directory,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Chip Size (cells)".to_owned(),
+            flags: vec!["--chip_size".to_owned()],
+            description: "Width and height of the chips, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("256".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Stride (cells)".to_owned(),
+            flags: vec!["--stride".to_owned()],
+            description: "Spacing of the chips, in grid cells; defaults to the chip size.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Augmentation".to_owned(),
+            flags: vec!["--augment".to_owned()],
+            description: "Transformed copies of each chip to export; one of 'none', 'flips', 'rotations', and 'all'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "none".to_owned(),
+                "flips".to_owned(),
+                "rotations".to_owned(),
+                "all".to_owned(),
+            ]),
+            default_value: Some("none".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Background Class".to_owned(),
+            flags: vec!["--background".to_owned()],
+            description: "Class value of unlabelled cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Labelled Fraction".to_owned(),
+            flags: vec!["--min_labelled".to_owned()],
+            description: "Minimum fraction (0-1) of a chip's cells with a class other than the background.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum NoData Fraction".to_owned(),
+            flags: vec!["--max_nodata".to_owned()],
+            description: "Maximum fraction (0-1) of a chip's cells that may be NoData.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Chips per Class (optional)".to_owned(),
+            flags: vec!["--max_per_class".to_owned()],
+            description: "Maximum number of chips exported for each dominant class.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Random Seed".to_owned(),
+            flags: vec!["--seed".to_owned()],
+            description: "Seed of the random selection of chips when there is a maximum per class.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("42".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Alignment".to_owned(),
+            flags: vec!["--align".to_owned()],
+            description: "Input alignment mode; 'auto' resamples the other inputs to the grid of the first band.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["none".to_owned(), "auto".to_owned()]),
+            default_value: Some("none".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='red.tif;green.tif;blue.tif;nir.tif' --label_polys=landcover.shp --field=CLASS --outdir=\"*path*to*chips*\" --chip_size=256 --stride=128 --augment=flips --min_labelled=0.1 --max_per_class=500", short_exe, name).replace("*", &sep);
+
+        ExportImageChips {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExportImageChips {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_list("inputs");
+        let mut labels_file = parsed.get_string("labels").unwrap_or_default();
+        let mut polys_file = parsed.get_string("label_polys").unwrap_or_default();
+        let field_name = parsed.get_string("field").unwrap_or_default();
+        let mut output_directory = parsed.get_string("outdir").unwrap_or_default();
+        let chip_size = parsed.get_usize("chip_size")?.unwrap_or(256);
+        let mut stride = parsed.get_usize("stride")?.unwrap_or(0);
+        let augment = parsed.get_string("augment").unwrap_or_default();
+        let background = parsed.get_i64("background").unwrap_or(0) as i32;
+        let min_labelled = parsed.get_f64("min_labelled").unwrap_or(0f64);
+        let max_nodata = parsed.get_f64("max_nodata").unwrap_or(0f64);
+        let max_per_class = parsed.get_usize("max_per_class")?.unwrap_or(usize::MAX);
+        let seed = parsed.get_i64("seed").unwrap_or(42) as u64;
+        let align = parsed.get_string("align").unwrap_or_default() == "auto";
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if labels_file.is_empty() == polys_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either a label raster (--labels) or a label polygon file (--label_polys), but not both, must be specified.",
+            ));
+        }
+        if output_directory.trim().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "An output directory (--outdir) must be specified.",
+            ));
+        }
+        if chip_size < 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The chip size must be at least 2 grid cells.",
+            ));
+        }
+        if stride == 0 {
+            stride = chip_size;
+        }
+        // the transformations of the chips that are exported
+        let transforms = match augment.as_str() {
+            "none" => vec![Transform::None],
+            "flips" => vec![Transform::None, Transform::FlipH, Transform::FlipV],
+            "rotations" => vec![
+                Transform::None,
+                Transform::Rot90,
+                Transform::Rot180,
+                Transform::Rot270,
+            ],
+            // "all"; the other values are rejected by the argument parser
+            _ => vec![
+                Transform::None,
+                Transform::FlipH,
+                Transform::FlipV,
+                Transform::Rot90,
+                Transform::Rot180,
+                Transform::Rot270,
+                Transform::Transpose,
+                Transform::Transverse,
+            ],
+        };
+
+        if input_files.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one input image band (--inputs) must be specified.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let mut bands: Vec<Raster> = Vec::with_capacity(input_files.len());
+        for f in &input_files {
+            let file_name = resolve_input_path(f, working_directory)?;
+            let band = if bands.is_empty() {
+                Raster::new(&file_name, "r")?
+            } else {
+                read_secondary_raster(&file_name, &bands[0], align, true, verbose)?
+            };
+            bands.push(band);
+        }
+        let num_bands = bands.len();
+        let rows = bands[0].configs.rows;
+        let columns = bands[0].configs.columns;
+
+        let labels = if !labels_file.is_empty() {
+            labels_file = resolve_input_path(&labels_file, working_directory)?;
+            read_secondary_raster(&labels_file, &bands[0], align, false, verbose)?
+        } else {
+            // Rasterize the polygons to the grid of the image.
+            polys_file = resolve_input_path(&polys_file, working_directory)?;
+            let memory_file = "memory:export_image_chips_labels";
+            let args = vec![
+                format!("--input={}", polys_file),
+                format!("--field={}", field_name),
+                format!("--output={}", memory_file),
+                format!("--base={}", bands[0].file_name),
+            ];
+            VectorPolygonsToRaster::new().run(args, working_directory, false, reporter)?;
+            let labels = Raster::new(memory_file, "r");
+            remove_memory_raster(memory_file);
+            let mut labels = labels?;
+            // cells outside of the polygons have the rasterizer's background value of zero
+            if background != 0 {
+                for row in 0..rows as isize {
+                    reporter.check_cancelled()?;
+                    for col in 0..columns as isize {
+                        if labels.get_value(row, col) == 0f64 {
+                            labels.set_value(row, col, background as f64);
+                        }
+                    }
+                }
+            }
+            labels
+        };
+        let label_nodata = labels.configs.nodata;
+
+        output_directory = resolve_path(&output_directory, working_directory);
+        let out_dir = path::Path::new(&output_directory);
+        fs::create_dir_all(out_dir.join("images"))?;
+        fs::create_dir_all(out_dir.join("labels"))?;
+
+        let start = Instant::now();
+
+        if chip_size > rows || chip_size > columns {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The chip size is larger than the image.",
+            ));
+        }
+
+        // Find the chips that satisfy the NoData and labelling criteria.
+        let num_cells = (chip_size * chip_size) as f64;
+        let mut candidates: Vec<Chip> = vec![];
+        let chip_rows = (0..=rows - chip_size).step_by(stride).collect::<Vec<usize>>();
+        let chip_cols = (0..=columns - chip_size).step_by(stride).collect::<Vec<usize>>();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for (i, &row) in chip_rows.iter().enumerate() {
+            for &col in &chip_cols {
+                let mut num_nodata = 0usize;
+                let mut num_labelled = 0usize;
+                let mut class_counts: HashMap<i32, usize> = HashMap::new();
+                for r in row..row + chip_size {
+                    for c in col..col + chip_size {
+                        if bands.iter().any(|b| b.is_nodata(b.get_value(r as isize, c as isize))) {
+                            num_nodata += 1;
+                        }
+                        let z = labels.get_value(r as isize, c as isize);
+                        if !is_nodata(z, label_nodata) {
+                            let class = z.round() as i32;
+                            if class != background {
+                                num_labelled += 1;
+                                *class_counts.entry(class).or_insert(0) += 1;
+                            }
+                        }
+                    }
+                }
+                let labelled = num_labelled as f64 / num_cells;
+                if num_nodata as f64 / num_cells > max_nodata || labelled < min_labelled {
+                    continue;
+                }
+                // the most frequent class, with ties going to the smaller class value
+                let class = class_counts
+                    .iter()
+                    .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+                    .map(|(&class, _)| class)
+                    .unwrap_or(background);
+                candidates.push(Chip { row, col, class, labelled });
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * i as f64 / (chip_rows.len() - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Finding chips", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Balance the classes by randomly selecting up to the maximum number of chips of each.
+        if max_per_class < usize::MAX {
+            let mut rng = StdRng::seed_from_u64(seed);
+            candidates.shuffle(&mut rng);
+            let mut selected: HashMap<i32, usize> = HashMap::new();
+            candidates.retain(|chip| {
+                let n = selected.entry(chip.class).or_insert(0);
+                *n += 1;
+                *n <= max_per_class
+            });
+            candidates.sort_by(|a, b| (a.row, a.col).cmp(&(b.row, b.col)));
+        }
+        if candidates.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No chips satisfy the NoData and labelling criteria.",
+            ));
+        }
+
+        // Write the chips and the index.
+        let index_file = out_dir.join("index.csv");
+        let f = File::create(&index_file)?;
+        let mut writer = BufWriter::new(f);
+        writer.write_all(
+            "CHIP,IMAGE,LABEL,ROW,COLUMN,X_MIN,Y_MAX,AUGMENTATION,CLASS,LABELLED\n".as_bytes(),
+        )?;
+        let res_x = bands[0].configs.resolution_x;
+        let res_y = bands[0].configs.resolution_y;
+        let west = bands[0].configs.west;
+        let north = bands[0].configs.north;
+        let mut image_data = vec![0f32; num_bands * chip_size * chip_size];
+        let mut label_data = vec![0i32; chip_size * chip_size];
+        let mut class_chips: HashMap<i32, usize> = HashMap::new();
+        for (n, chip) in candidates.iter().enumerate() {
+            for transform in &transforms {
+                for i in 0..chip_size {
+                    for j in 0..chip_size {
+                        let (si, sj) = transform.source_cell(i, j, chip_size);
+                        let (r, c) = ((chip.row + si) as isize, (chip.col + sj) as isize);
+                        for (b, band) in bands.iter().enumerate() {
+                            let z = band.get_value(r, c);
+                            image_data[(b * chip_size + i) * chip_size + j] = if !is_nodata(z, band.configs.nodata) {
+                                z as f32
+                            } else {
+                                f32::NAN
+                            };
+                        }
+                        let z = labels.get_value(r, c);
+                        label_data[i * chip_size + j] = if !is_nodata(z, label_nodata) { z.round() as i32 } else { -1 };
+                    }
+                }
+                let chip_name = match transform.suffix() {
+                    Some(suffix) => format!("chip_{:06}_{}", n + 1, suffix),
+                    None => format!("chip_{:06}", n + 1),
+                };
+                let image_name = format!("images/{}.npy", chip_name);
+                let label_name = format!("labels/{}.npy", chip_name);
+                write_npy(
+                    &out_dir.join(&image_name),
+                    "<f4",
+                    &[num_bands, chip_size, chip_size],
+                    image_data.iter().flat_map(|v| v.to_le_bytes()).collect(),
+                )?;
+                write_npy(
+                    &out_dir.join(&label_name),
+                    "<i4",
+                    &[chip_size, chip_size],
+                    label_data.iter().flat_map(|v| v.to_le_bytes()).collect(),
+                )?;
+                writer.write_all(
+                    format!(
+                        "{},{},{},{},{},{},{},{},{},{:.4}\n",
+                        chip_name,
+                        image_name,
+                        label_name,
+                        chip.row,
+                        chip.col,
+                        west + chip.col as f64 * res_x,
+                        north - chip.row as f64 * res_y,
+                        transform.suffix().unwrap_or("none"),
+                        chip.class,
+                        chip.labelled
+                    )
+                    .as_bytes(),
+                )?;
+            }
+            *class_chips.entry(chip.class).or_insert(0) += 1;
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * n as f64 / (candidates.len() - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Writing chips", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        writer.flush()?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!(
+                "Exported {} chips ({} files with augmentation) of {} candidate locations",
+                candidates.len(),
+                candidates.len() * transforms.len(),
+                chip_rows.len() * chip_cols.len()
+            );
+            let mut classes = class_chips.iter().collect::<Vec<_>>();
+            classes.sort();
+            for (class, n) in classes {
+                println!("Class {}: {} chips", class, n);
+            }
+            println!("Index file: {}", index_file.to_string_lossy());
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+struct Chip {
+    row: usize,
+    col: usize,
+    class: i32,
+    labelled: f64,
+}
+
+/// The flips and rotations of a square chip.
+#[derive(Clone, Copy)]
+enum Transform {
+    None,
+    FlipH,
+    FlipV,
+    Rot90,
+    Rot180,
+    Rot270,
+    Transpose,
+    Transverse,
+}
+
+impl Transform {
+    fn suffix(&self) -> Option<&'static str> {
+        match self {
+            Transform::None => None,
+            Transform::FlipH => Some("fliph"),
+            Transform::FlipV => Some("flipv"),
+            Transform::Rot90 => Some("rot90"),
+            Transform::Rot180 => Some("rot180"),
+            Transform::Rot270 => Some("rot270"),
+            Transform::Transpose => Some("transpose"),
+            Transform::Transverse => Some("transverse"),
+        }
+    }
+
+    /// Returns the cell of the original chip that is at cell (i, j) of the transformed chip.
+    /// Rotations are counter-clockwise, as in numpy.rot90.
+    fn source_cell(&self, i: usize, j: usize, n: usize) -> (usize, usize) {
+        match self {
+            Transform::None => (i, j),
+            Transform::FlipH => (i, n - 1 - j),
+            Transform::FlipV => (n - 1 - i, j),
+            Transform::Rot90 => (j, n - 1 - i),
+            Transform::Rot180 => (n - 1 - i, n - 1 - j),
+            Transform::Rot270 => (n - 1 - j, i),
+            Transform::Transpose => (j, i),
+            Transform::Transverse => (n - 1 - j, n - 1 - i),
+        }
+    }
+}
+
+/// Writes an array of little-endian values to a NumPy (.npy, version 1.0) file.
+fn write_npy(file_name: &path::Path, descr: &str, shape: &[usize], data: Vec<u8>) -> Result<(), Error> {
+    let shape_str = shape.iter().map(|s| format!("{}, ", s)).collect::<String>();
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': ({}), }}",
+        descr,
+        shape_str.trim_end_matches(' ')
+    );
+    // The header is padded so that the data are 64-byte aligned.
+    let unpadded = 10 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+    let f = File::create(file_name)?;
+    let mut writer = BufWriter::new(f);
+    writer.write_all(b"\x93NUMPY\x01\x00")?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    writer.write_all(&data)?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,667 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::mbtiles::MbTilesWriter;
+use super::rendering::*;
+use whitebox_raster::*;
+use whitebox_common::spatial_ref_system::{Crs, EARTH_RADIUS};
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::f64::consts::PI;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::path::PathBuf;
+use whitebox_common::errors::ErrorCause;
+
+const TILE_SIZE: usize = 256;
+// the latitude at which the Web Mercator grid is square
+const MAX_LATITUDE: f64 = 85.0511287798066;
+
+/// This tool renders a raster into a pyramid of web map tiles, which can be added directly to web
+/// maps (e.g. Leaflet, OpenLayers and MapLibre) and desktop GIS software. Tiles are 256 by 256 pixel PNG
+/// images in the Web Mercator projection (EPSG:3857), on the standard global grid of tiles in which zoom
+/// level *z* has 2<sup>*z*</sup> by 2<sup>*z*</sup> tiles. The output (`--output`) is written in one of
+/// the formats (`--format`):
+///
+/// - `xyz`: a directory of tiles, `z/x/y.png`, with rows (*y*) counted from the top of the grid, the
+///   scheme used by most web maps.
+/// - `tms`: a directory of tiles, `z/x/y.png`, with rows counted from the bottom of the grid, as in the
+///   Tile Map Service specification.
+/// - `mbtiles`: an MBTiles file, the SQLite database of tiles used by many tile servers and mobile apps.
+///
+/// By default, the format is `mbtiles` if the output file name has the extension .mbtiles and `xyz`
+/// otherwise. Directory outputs also contain a `metadata.json` file with the bounds, centre and zoom
+/// levels of the tiles, which are stored in the metadata table of MBTiles files.
+///
+/// The input (`--input`) is rendered either using its colours, if it is an RGB colour raster (e.g. the
+/// output of `CreateColourComposite`, `ExportColourComposite` or `ColourizeRaster`), or else using a
+/// colour ramp (`--palette`) spanning a range of its values, as in the `ColourizeRaster` tool: a built-in
+/// ramp (`grey`, `viridis`, `magma`, `terrain`, `spectral`, `blue_white_red`, `brown_green` or `blues`)
+/// or a palette file, optionally reversed (`--reverse`), spanning the range from `--min_value` to
+/// `--max_value` or the range of values after clipping the percentage `--clip` of cells from each tail of
+/// the distribution. NoData cells and the areas around the input are transparent, and tiles that would be
+/// entirely transparent are not written.
+///
+/// The input is reprojected to Web Mercator, and must be in geographic coordinates, Web Mercator, or a
+/// UTM zone of the WGS84 or NAD83 datums; other inputs must be reprojected first. Cells are sampled by
+/// nearest-neighbour at the highest zoom level (`--max_zoom`), which by default is the level at which the
+/// size of the tiles' pixels is closest to, without exceeding, the input's cell size. The tiles of lower
+/// zoom levels, down to `--min_zoom` (by default, the highest level at which the input's extent is no
+/// larger than a single tile), are overviews created by averaging the tiles beneath them. Note that the
+/// number of tiles increases four-fold with each zoom level.
+///
+/// # See Also
+/// `ColourizeRaster`, `ExportColourComposite`
+pub struct ExportWebTiles {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ExportWebTiles {
+    pub fn new() -> ExportWebTiles {
+        // public constructor
+        let name = "ExportWebTiles".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Renders a raster into a pyramid of Web Mercator map tiles (XYZ, TMS or MBTiles)."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File or Directory".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output MBTiles (.mbtiles) file or tile directory.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tile Format (optional)".to_owned(),
+            flags: vec!["--format".to_owned()],
+            description: "Output tile format; one of 'xyz', 'tms', and 'mbtiles'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "xyz".to_owned(),
+                "tms".to_owned(),
+                "mbtiles".to_owned(),
+            ]),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Zoom Level (optional)".to_owned(),
+            flags: vec!["--min_zoom".to_owned()],
+            description: "Lowest zoom level of the tiles.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Zoom Level (optional)".to_owned(),
+            flags: vec!["--max_zoom".to_owned()],
+            description: "Highest zoom level of the tiles.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Palette".to_owned(),
+            flags: vec!["--palette".to_owned()],
+            description: "Built-in colour ramp name (e.g. 'viridis', 'terrain') or palette file, for inputs that are not RGB.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("viridis".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reverse palette?".to_owned(),
+            flags: vec!["--reverse".to_owned()],
+            description: "Optional flag to reverse the colours of the palette.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Value (optional)".to_owned(),
+            flags: vec!["--min_value".to_owned()],
+            description: "Value at the low end of the colour ramp.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Value (optional)".to_owned(),
+            flags: vec!["--max_value".to_owned()],
+            description: "Value at the high end of the colour ramp.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tail Clip Percent".to_owned(),
+            flags: vec!["--clip".to_owned()],
+            description: "Percentage of cells clipped from each tail of the distribution to find the range of values.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif -o=dem.mbtiles --palette=terrain --min_zoom=8 --max_zoom=14", short_exe, name).replace("*", &sep);
+
+        ExportWebTiles {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ExportWebTiles {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let format = parsed.get_string("format");
+        let min_zoom = parsed.get_usize("min_zoom")?;
+        let max_zoom = parsed.get_usize("max_zoom")?;
+        let palette = parsed.get_string("palette").unwrap_or("viridis".to_string());
+        let reverse = parsed.get_bool("reverse");
+        let min_value = parsed.get_f64("min_value");
+        let max_value = parsed.get_f64("max_value");
+        let clip = parsed.get_f64("clip").unwrap_or(0f64);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if clip < 0f64 || clip >= 50f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The tail clip percent must be between 0 and 50.",
+            ));
+        }
+        let format = format.unwrap_or(if output_file.to_lowercase().ends_with(".mbtiles") {
+            "mbtiles".to_string()
+        } else {
+            "xyz".to_string()
+        });
+
+        if verbose {
+            println!("Reading data...")
+        };
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let crs = input_crs(&input)?;
+        let colouring = if input.configs.data_type == DataType::RGBA32
+            || input.configs.photometric_interp == PhotometricInterpretation::RGB
+        {
+            Colouring::Rgb
+        } else {
+            let mut ramp = ColourRamp::new(&palette, working_directory)?;
+            if reverse {
+                ramp.reverse();
+            }
+            let (mut low, mut high) = (0f64, 1f64);
+            if ramp.is_relative() {
+                if min_value.is_none() || max_value.is_none() {
+                    let (clip_low, clip_high) = input.calculate_clip_values(clip);
+                    low = clip_low;
+                    high = clip_high;
+                }
+                low = min_value.unwrap_or(low);
+                high = max_value.unwrap_or(high);
+                if low > high {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The minimum value must be less than the maximum value.",
+                    ));
+                }
+                if verbose {
+                    println!("Range of values: {} to {}", low, high);
+                }
+            }
+            Colouring::Ramp {
+                stops: ramp.resolve(low, high),
+                nodata: ramp.nodata_colour(),
+            }
+        };
+
+        // The geographic bounds of the input, found by tracing its edges.
+        let (mut west, mut east, mut south, mut north) = (180f64, -180f64, 90f64, -90f64);
+        let num_samples = 100;
+        for i in 0..=num_samples {
+            let t = i as f64 / num_samples as f64;
+            let x = input.configs.west + t * (input.configs.east - input.configs.west);
+            let y = input.configs.south + t * (input.configs.north - input.configs.south);
+            for &(px, py) in &[
+                (x, input.configs.north),
+                (x, input.configs.south),
+                (input.configs.west, y),
+                (input.configs.east, y),
+            ] {
+                let (lon, lat) = crs.to_lon_lat(px, py);
+                west = west.min(lon);
+                east = east.max(lon);
+                south = south.min(lat);
+                north = north.max(lat);
+            }
+        }
+        west = west.max(-180f64);
+        east = east.min(180f64);
+        south = south.max(-MAX_LATITUDE);
+        north = north.min(MAX_LATITUDE);
+        if west >= east || south >= north {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input does not overlap the extent of the Web Mercator projection.",
+            ));
+        }
+
+        // The default zoom levels: the highest level with pixels no larger than the input's cells
+        // and the highest level at which the input's extent is no larger than a single tile.
+        let mid_lat = ((north + south) / 2f64).to_radians();
+        let cell_size = match crs {
+            Crs::Geographic => input.configs.resolution_x * 111320f64 * mid_lat.cos(),
+            Crs::WebMercator => input.configs.resolution_x * mid_lat.cos(),
+            _ => input.configs.resolution_x,
+        };
+        let default_max_zoom = (2f64 * PI * EARTH_RADIUS * mid_lat.cos() / (TILE_SIZE as f64 * cell_size))
+            .log2()
+            .ceil()
+            .max(0f64)
+            .min(22f64) as usize;
+        let max_zoom = max_zoom.unwrap_or(default_max_zoom);
+        let (x_min, y_min) = tile_coordinates(west, north, 0);
+        let (x_max, y_max) = tile_coordinates(east, south, 0);
+        let span = (x_max - x_min).max(y_max - y_min);
+        let default_min_zoom = ((1f64 / span).log2().floor().max(0f64) as usize).min(max_zoom);
+        let min_zoom = min_zoom.unwrap_or(default_min_zoom);
+        if max_zoom > 24 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum zoom level must be no greater than 24.",
+            ));
+        }
+        if min_zoom > max_zoom {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The minimum zoom level must not be greater than the maximum zoom level.",
+            ));
+        }
+
+        // The range of tiles covering the input at each zoom level
+        let tile_ranges: Vec<(u32, u32, u32, u32)> = (0..=max_zoom)
+            .map(|z| {
+                let n = 1u64 << z;
+                let (x0, y0) = tile_coordinates(west, north, z);
+                let (x1, y1) = tile_coordinates(east, south, z);
+                let last = |v: f64| ((v.ceil() as u64).max(1) - 1).min(n - 1) as u32;
+                (x0.floor() as u32, last(x1), y0.floor() as u32, last(y1))
+            })
+            .collect();
+        let (x0, x1, y0, y1) = tile_ranges[max_zoom];
+        let num_leaf_tiles = (x1 - x0 + 1) as usize * (y1 - y0 + 1) as usize;
+        if verbose {
+            println!(
+                "Zoom levels: {} to {} ({} tiles at level {})",
+                min_zoom, max_zoom, num_leaf_tiles, max_zoom
+            );
+        }
+
+        let output = if format == "mbtiles" {
+            TileOutput::MbTiles(MbTilesWriter::create(&output_file)?)
+        } else {
+            fs::create_dir_all(&output_file)?;
+            TileOutput::Directory {
+                path: PathBuf::from(&output_file),
+                tms: format == "tms",
+            }
+        };
+        let mut renderer = TileRenderer {
+            input: &input,
+            crs: crs,
+            colouring: colouring,
+            min_zoom: min_zoom,
+            max_zoom: max_zoom,
+            tile_ranges: tile_ranges,
+            output: output,
+            num_tiles: 0,
+            num_leaf_tiles: num_leaf_tiles,
+            num_leaves_rendered: 0,
+            old_progress: 1,
+            verbose: verbose,
+            reporter: reporter,
+        };
+        let (x0, x1, y0, y1) = renderer.tile_ranges[min_zoom];
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                renderer.render(min_zoom, x, y)?;
+            }
+        }
+        let num_tiles = renderer.num_tiles;
+
+        let name = path::Path::new(&output_file)
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let centre_zoom = (min_zoom + max_zoom) / 2;
+        let metadata = vec![
+            ("name".to_string(), name),
+            ("format".to_string(), "png".to_string()),
+            ("type".to_string(), "overlay".to_string()),
+            ("version".to_string(), "1".to_string()),
+            (
+                "description".to_string(),
+                format!("Created by whitebox_tools\' {} tool from {}", self.get_tool_name(), input.get_short_filename()),
+            ),
+            ("bounds".to_string(), format!("{},{},{},{}", west, south, east, north)),
+            (
+                "center".to_string(),
+                format!("{},{},{}", (west + east) / 2f64, (south + north) / 2f64, centre_zoom),
+            ),
+            ("minzoom".to_string(), min_zoom.to_string()),
+            ("maxzoom".to_string(), max_zoom.to_string()),
+        ];
+        match renderer.output {
+            TileOutput::MbTiles(writer) => writer.finish(&metadata)?,
+            TileOutput::Directory { path, tms } => {
+                let mut json = serde_json::Map::new();
+                for (name, value) in metadata {
+                    json.insert(name, serde_json::Value::String(value));
+                }
+                json.insert(
+                    "scheme".to_string(),
+                    serde_json::Value::String(if tms { "tms" } else { "xyz" }.to_string()),
+                );
+                fs::write(
+                    path.join("metadata.json"),
+                    serde_json::to_string_pretty(&json).unwrap_or_default(),
+                )?;
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Tiles written: {}", num_tiles);
+            println!(
+                "{}",
+                &format!("Elapsed Time (including I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the position of a geographic coordinate on the Web Mercator grid of tiles at a zoom
+/// level, in units of tiles, with rows counted from the top of the grid.
+fn tile_coordinates(lon: f64, lat: f64, zoom: usize) -> (f64, f64) {
+    let n = (1u64 << zoom) as f64;
+    let lat = lat.to_radians();
+    let x = (lon + 180f64) / 360f64 * n;
+    let y = (1f64 - (lat.tan() + 1f64 / lat.cos()).ln() / PI) / 2f64 * n;
+    (x, y)
+}
+
+/// Returns the coordinate reference system of the input, if it is one that the tool can reproject.
+fn input_crs(input: &Raster) -> Result<Crs, Error> {
+    match Crs::from_definition(
+        input.configs.epsg_code,
+        &input.configs.coordinate_ref_system_wkt,
+        input.is_in_geographic_coordinates(),
+    ) {
+        Some(crs) => Ok(crs),
+        None => Err(ErrorCause::UnsupportedFormat.error(
+            format!(
+                "The coordinate reference system of the input (EPSG code {}) is not supported; it must be geographic, Web Mercator, or a WGS84, NAD83, or ETRS89 UTM zone.",
+                input.configs.epsg_code
+            ),
+        )),
+    }
+}
+
+/// How the input's values are rendered as colours.
+enum Colouring {
+    Rgb,
+    Ramp { stops: Vec<(f64, [f64; 4])>, nodata: Rgba },
+}
+
+enum TileOutput {
+    Directory { path: PathBuf, tms: bool },
+    MbTiles(MbTilesWriter),
+}
+
+/// Renders the tile pyramid depth-first, so that only the tiles beneath the one being rendered are
+/// held in memory.
+struct TileRenderer<'a> {
+    input: &'a Raster,
+    crs: Crs,
+    colouring: Colouring,
+    min_zoom: usize,
+    max_zoom: usize,
+    // the columns and rows of the tiles covering the input at each zoom level
+    tile_ranges: Vec<(u32, u32, u32, u32)>,
+    output: TileOutput,
+    num_tiles: usize,
+    num_leaf_tiles: usize,
+    num_leaves_rendered: usize,
+    old_progress: usize,
+    verbose: bool,
+    reporter: &'a dyn ProgressReporter,
+}
+
+impl<'a> TileRenderer<'a> {
+    /// Renders a tile and writes it, if it is within the zoom levels of the output and is not
+    /// entirely transparent. Returns the pixels of the tile, or None for tiles outside of the input.
+    fn render(&mut self, zoom: usize, x: u32, y: u32) -> Result<Option<Vec<Rgba>>, Error> {
+        let (x0, x1, y0, y1) = self.tile_ranges[zoom];
+        if x < x0 || x > x1 || y < y0 || y > y1 {
+            return Ok(None);
+        }
+        let pixels = if zoom == self.max_zoom {
+            let pixels = self.sample(zoom, x, y);
+            self.reporter.check_cancelled()?;
+            self.num_leaves_rendered += 1;
+            if self.verbose {
+                let progress = (100.0_f64 * self.num_leaves_rendered as f64 / self.num_leaf_tiles as f64) as usize;
+                if progress != self.old_progress {
+                    self.reporter.progress("Rendering tiles", progress);
+                    self.old_progress = progress;
+                }
+            }
+            pixels
+        } else {
+            // an overview, averaging the tiles of the next zoom level
+            let mut pixels = vec![TRANSPARENT; TILE_SIZE * TILE_SIZE];
+            let half = TILE_SIZE / 2;
+            for (i, &(dx, dy)) in [(0u32, 0u32), (1, 0), (0, 1), (1, 1)].iter().enumerate() {
+                let child = match self.render(zoom + 1, 2 * x + dx, 2 * y + dy)? {
+                    Some(child) => child,
+                    None => continue,
+                };
+                let (col_offset, row_offset) = ((i % 2) * half, (i / 2) * half);
+                for row in 0..half {
+                    for col in 0..half {
+                        let mut sums = [0f64; 4];
+                        for &(r, c) in &[(0, 0), (0, 1), (1, 0), (1, 1)] {
+                            let p = child[(2 * row + r) * TILE_SIZE + 2 * col + c];
+                            let alpha = p[3] as f64;
+                            for k in 0..3 {
+                                sums[k] += p[k] as f64 * alpha;
+                            }
+                            sums[3] += alpha;
+                        }
+                        if sums[3] > 0f64 {
+                            pixels[(row_offset + row) * TILE_SIZE + col_offset + col] = [
+                                (sums[0] / sums[3]).round() as u8,
+                                (sums[1] / sums[3]).round() as u8,
+                                (sums[2] / sums[3]).round() as u8,
+                                (sums[3] / 4f64).round() as u8,
+                            ];
+                        }
+                    }
+                }
+            }
+            pixels
+        };
+        if zoom >= self.min_zoom && pixels.iter().any(|p| p[3] > 0) {
+            self.write(zoom, x, y, &pixels)?;
+        }
+        Ok(Some(pixels))
+    }
+
+    /// Samples the input at the centres of the pixels of a tile.
+    fn sample(&self, zoom: usize, x: u32, y: u32) -> Vec<Rgba> {
+        let configs = &self.input.configs;
+        let nodata = configs.nodata;
+        let n = ((1u64 << zoom) * TILE_SIZE as u64) as f64;
+        let mut pixels = vec![TRANSPARENT; TILE_SIZE * TILE_SIZE];
+        for py in 0..TILE_SIZE {
+            let v = (y as usize * TILE_SIZE + py) as f64 + 0.5;
+            let lat = (PI * (1f64 - 2f64 * v / n)).sinh().atan().to_degrees();
+            for px in 0..TILE_SIZE {
+                let u = (x as usize * TILE_SIZE + px) as f64 + 0.5;
+                let lon = u / n * 360f64 - 180f64;
+                let (sx, sy) = self.crs.from_lon_lat(lon, lat);
+                let col = ((sx - configs.west) / configs.resolution_x).floor();
+                let row = ((configs.north - sy) / configs.resolution_y).floor();
+                if col < 0f64 || row < 0f64 || col >= configs.columns as f64 || row >= configs.rows as f64 {
+                    continue;
+                }
+                let z = self.input.get_value(row as isize, col as isize);
+                pixels[py * TILE_SIZE + px] = match &self.colouring {
+                    Colouring::Rgb => {
+                        if is_nodata(z, nodata) {
+                            TRANSPARENT
+                        } else {
+                            let v = z as u32;
+                            [v as u8, (v >> 8) as u8, (v >> 16) as u8, (v >> 24) as u8]
+                        }
+                    }
+                    Colouring::Ramp { stops, nodata: nodata_colour } => {
+                        if is_nodata(z, nodata) {
+                            *nodata_colour
+                        } else {
+                            let c = ramp_colour(stops, z);
+                            [
+                                c[0].round() as u8,
+                                c[1].round() as u8,
+                                c[2].round() as u8,
+                                c[3].round() as u8,
+                            ]
+                        }
+                    }
+                };
+            }
+        }
+        pixels
+    }
+
+    fn write(&mut self, zoom: usize, x: u32, y: u32, pixels: &[Rgba]) -> Result<(), Error> {
+        let png = encode_png(TILE_SIZE, TILE_SIZE, pixels);
+        // the row of the tile counted from the bottom of the grid
+        let tms_y = (1u32 << zoom) - 1 - y;
+        match &mut self.output {
+            TileOutput::MbTiles(writer) => writer.add_tile(zoom as u32, x, tms_y, &png)?,
+            TileOutput::Directory { path, tms } => {
+                let dir = path.join(zoom.to_string()).join(x.to_string());
+                fs::create_dir_all(&dir)?;
+                let row = if *tms { tms_y } else { y };
+                fs::write(dir.join(format!("{}.png", row)), &png)?;
+            }
+        }
+        self.num_tiles += 1;
+        Ok(())
+    }
+}
@@ -12,7 +12,7 @@ use crate::tools::*;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// The tool is somewhat modified from Dr. Kovesi's original Matlab code in that it
@@ -132,52 +132,12 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut sigma = 1.8;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-sigma" {
-                if keyval {
-                    sigma = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    sigma = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut sigma = parsed.get_f64("sigma").unwrap_or(1.8);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -190,14 +150,8 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut progress: usize;
         let mut old_progress: usize = 1;
@@ -275,7 +229,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     // It's an RGB image. Get the intensity value from the IHS decomposition of the RGB value.
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             return value2i(value);
                         }
                         nodata
@@ -288,7 +242,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     sum_n = 0;
                     for col in 0..columns {
                         val = input_fn(row, col);
-                        if val == nodata {
+                        if is_nodata(val, nodata) {
                             val = 0f64;
                         } else {
                             sum_n += 1;
@@ -312,7 +266,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     sum = 0f64;
                     for col in 0..columns {
                         val = output.get_value(row, col);
-                        if val == nodata {
+                        if is_nodata(val, nodata) {
                             val = 0f64;
                         }
                         sum += val;
@@ -340,7 +294,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     }
 
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata(input.get_value(row, col), nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -364,15 +318,11 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!(
-                                "Progress (Loop {} of {}): {}%",
-                                iteration_num + 1,
-                                n,
-                                progress
-                            );
+                            reporter.progress(&format!("Progress (Loop {} of {})", iteration_num + 1, n), progress);
                             old_progress = progress;
                         }
                     }
@@ -386,7 +336,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                 } else {
                     // convert it back into an rgb value, using the modified intensity value.
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             return hsi2value(h, s, value);
                         }
@@ -406,7 +356,7 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                     }
 
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata(input.get_value(row, col), nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -432,15 +382,11 @@ impl WhiteboxTool for FastAlmostGaussianFilter {
                             output.set_value(row, col, val);
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!(
-                                "Progress (Loop {} of {}): {}%",
-                                iteration_num + 1,
-                                n,
-                                progress
-                            );
+                            reporter.progress(&format!("Progress (Loop {} of {})", iteration_num + 1, n), progress);
                             old_progress = progress;
                         }
                     }
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to flip, or reflect, an image (`--input`) either vertically, horizontally, or both. The
 /// axis of reflection is specified using the `--direction` parameter. The input image is not reflected in place;
@@ -56,7 +55,7 @@ impl FlipImage {
 
         parameters.push(ToolParameter{
             name: "Direction".to_owned(), 
-            flags: vec!["--direction".to_owned()], 
+            flags: vec!["--dir".to_owned(), "--direction".to_owned()], 
             description: "Direction of reflection; options include 'v' (vertical), 'h' (horizontal), and 'b' (both).".to_owned(),
             parameter_type: ParameterType::OptionList(vec!["vertical".to_owned(), "horizontal".to_owned(), "both".to_owned()]),
             default_value: Some("vertical".to_owned()),
@@ -125,53 +124,23 @@ impl WhiteboxTool for FlipImage {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut direction = String::from("v");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-dir" || vec[0].to_lowercase() == "--direction" {
-                if keyval {
-                    direction = vec[1].to_string();
-                } else {
-                    direction = args[i + 1].to_string();
-                }
-                if direction.to_lowercase().contains("v") {
-                    direction = "v".to_string();
-                } else if direction.to_lowercase().contains("h")
-                    && !direction.to_lowercase().contains("b")
-                {
-                    direction = "h".to_string();
-                } else {
-                    direction = "b".to_string();
-                }
+        if let Some(value) = parsed.get_string("direction") {
+            direction = value;
+            if direction.to_lowercase().contains("v") {
+                direction = "v".to_string();
+            } else if direction.to_lowercase().contains("h")
+                && !direction.to_lowercase().contains("b")
+            {
+                direction = "h".to_string();
+            } else {
+                direction = "b".to_string();
             }
         }
 
@@ -186,14 +155,8 @@ impl WhiteboxTool for FlipImage {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Arc::new(Raster::new(&input_file, "r")?);
 
@@ -207,18 +170,14 @@ impl WhiteboxTool for FlipImage {
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let direction = direction.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let rows_less_one = rows - 1;
                 let cols_less_one = columns - 1;
                 match &direction as &str {
@@ -228,7 +187,9 @@ impl WhiteboxTool for FlipImage {
                             for col in 0..columns {
                                 data[col as usize] = input[(rows_less_one - row, col)];
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     }
                     "h" => {
@@ -237,7 +198,9 @@ impl WhiteboxTool for FlipImage {
                             for col in 0..columns {
                                 data[col as usize] = input[(row, cols_less_one - col)];
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     }
                     _ => {
@@ -248,7 +211,9 @@ impl WhiteboxTool for FlipImage {
                                 data[col as usize] =
                                     input[(rows_less_one - row, cols_less_one - col)];
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     }
                 }
@@ -258,10 +223,11 @@ impl WhiteboxTool for FlipImage {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -12,7 +12,6 @@ NOTES: 1. The tool should be updated to take multiple file inputs.
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
@@ -20,7 +19,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a Gaussian stretch on a raster image. The observed histogram of the input image is fitted
 /// to a Gaussian histogram, i.e. normal distribution. A histogram matching technique is used to map the values from
@@ -146,53 +145,12 @@ impl WhiteboxTool for GaussianContrastStretch {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut num_tones = 256f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-num_tones" {
-                num_tones = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut num_tones = parsed.get_f64("num_tones").unwrap_or(256f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -205,17 +163,11 @@ impl WhiteboxTool for GaussianContrastStretch {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if num_tones < 16f64 {
             println!("Warning: The output number of greytones must be at least 16. The value has been modified.");
@@ -264,9 +216,10 @@ impl WhiteboxTool for GaussianContrastStretch {
             let mut value: f64;
             let mut x: f64;
             for row in 0..rows {
+                reporter.check_cancelled()?;
                 for col in 0..columns {
                     value = input.get_value(row, col);
-                    if value != nodata {
+                    if !is_nodata(value, nodata) {
                         x = value2i(value); // gets the intensity
                         if x < min_val {
                             min_val = x;
@@ -291,7 +244,7 @@ impl WhiteboxTool for GaussianContrastStretch {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     if is_rgb_image {
                         z = value2i(z); // gets the intensity
                     }
@@ -303,10 +256,11 @@ impl WhiteboxTool for GaussianContrastStretch {
                     histogram[bin_num] += 1f64;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Loop 1 of 2: {}%", progress);
+                    reporter.progress("Loop 1 of 2", progress);
                     old_progress = progress;
                 }
             }
@@ -382,12 +336,8 @@ impl WhiteboxTool for GaussianContrastStretch {
         let reference_cdf = Arc::new(reference_cdf);
         let cdf = Arc::new(cdf);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
@@ -395,13 +345,13 @@ impl WhiteboxTool for GaussianContrastStretch {
             let reference_cdf = reference_cdf.clone();
             let cdf = cdf.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
                     Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             let v = value2i(value);
                             return v;
                         }
@@ -415,7 +365,7 @@ impl WhiteboxTool for GaussianContrastStretch {
                     })
                 } else {
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, (value + 3f64) / 6f64);
                             return ret;
@@ -434,7 +384,7 @@ impl WhiteboxTool for GaussianContrastStretch {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input_fn(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             bin_num = ((z - min_val) / bin_size) as usize;
                             if bin_num > num_bins_less_one {
                                 bin_num = num_bins_less_one;
@@ -462,7 +412,9 @@ impl WhiteboxTool for GaussianContrastStretch {
                             data[col as usize] = output_fn(row, col, x_val);
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -471,10 +423,11 @@ impl WhiteboxTool for GaussianContrastStretch {
         for r in 0..rows {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Loop 2 of 2: {}%", progress);
+                    reporter.progress("Loop 2 of 2", progress);
                     old_progress = progress;
                 }
             }
@@ -2,21 +2,19 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/06/2017
-Last Modified: 30/01/2020
+Last Modified: 16/10/2026
 License: MIT
 */
 
+use super::convolution::{convolve, ConvolutionInput, Kernel, NodataMode};
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
-use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool can be used to perform a Gaussian filter on a raster image. A Gaussian filter
 /// can be used to emphasize the longer-range variability in an image, effectively acting to
@@ -36,9 +34,14 @@ use std::thread;
 ///
 /// Like many low-pass filters, Gaussian filtering can significantly blur well-defined edges in
 /// the input image. The `EdgePreservingMeanFilter` and `BilateralFilter` offer more robust
-/// feature preservation during image smoothing. `GaussianFilter` is relatively slow compared to
-/// the `FastAlmostGaussianFilter` tool, which offers a fast-running approximatation to a
-/// Gaussian filter for larger kernel sizes.
+/// feature preservation during image smoothing. Because the Gaussian kernel is separable, the
+/// filter is applied as a horizontal pass followed by a vertical pass, which is considerably
+/// faster than applying the two-dimensional kernel directly. The `FastAlmostGaussianFilter` tool
+/// offers a fast-running approximatation to a Gaussian filter for very large kernel sizes.
+///
+/// When the tool is run with the `--compute=gpu` flag and WhiteboxTools has been built with the
+/// `gpu` feature, images are filtered on the GPU, in single precision. Systems without a
+/// suitable GPU fall back to the CPU.
 ///
 /// # See Also
 /// `FastAlmostGaussianFilter`, `MeanFilter`, `MedianFilter`, `RgbToIhs`
@@ -147,53 +150,13 @@ impl WhiteboxTool for GaussianFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut filter_size = 0usize;
-        let mut sigma_d = 0.75;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-sigma" {
-                if keyval {
-                    sigma_d = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    sigma_d = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let mut sigma_d = parsed.get_f64("sigma").unwrap_or(0.75);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -206,14 +169,8 @@ impl WhiteboxTool for GaussianFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if sigma_d < 0.5 {
             sigma_d = 0.5;
@@ -244,46 +201,21 @@ impl WhiteboxTool for GaussianFilter {
             filter_size = 3;
         }
 
-        let num_pixels_in_filter = filter_size * filter_size;
-        let mut d_x = vec![0isize; num_pixels_in_filter];
-        let mut d_y = vec![0isize; num_pixels_in_filter];
-        let mut weights = vec![0.0; num_pixels_in_filter];
-        let mut sum = 0f64;
-
-        // fill the filter d_x and d_y values and the distance-weights
-        let midpoint: isize = (filter_size as f64 / 2f64).floor() as isize; // + 1;
-        let mut a = 0;
-        let (mut x, mut y): (isize, isize);
-        for row in 0..filter_size {
-            for col in 0..filter_size {
-                x = col as isize - midpoint;
-                y = row as isize - midpoint;
-                d_x[a] = x;
-                d_y[a] = y;
-                weight = recip_root_2_pi_times_sigma_d
-                    * (-1.0 * ((x * x + y * y) as f64) / two_sigma_sqr_d).exp();
-                weights[a] = weight;
-                sum += weight;
-                a += 1;
-            }
-        }
-
-        for a in 0..num_pixels_in_filter {
-            weights[a] /= sum;
-        }
-
-        // let midpoint = (filter_size as f64 / 2f64).floor() as isize;
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
+        // The Gaussian kernel is separable into the product of two one-dimensional kernels.
+        let midpoint: isize = (filter_size as f64 / 2f64).floor() as isize;
+        let weights_1d: Vec<f64> = (0..filter_size as isize)
+            .map(|i| {
+                let x = i - midpoint;
+                recip_root_2_pi_times_sigma_d * (-1.0 * ((x * x) as f64) / two_sigma_sqr_d).exp()
+            })
+            .collect();
+        let kernel = Arc::new(Kernel::separable(weights_1d.clone(), weights_1d));
 
         if verbose {
             println!("Reading data...")
         };
 
         let input = Arc::new(Raster::new(&input_file, "r")?);
-        let d_x = Arc::new(d_x);
-        let d_y = Arc::new(d_y);
-        let weights = Arc::new(weights);
 
         let start = Instant::now();
 
@@ -296,91 +228,39 @@ impl WhiteboxTool for GaussianFilter {
             false
         };
 
-        let rows = input.configs.rows as isize;
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let d_x = d_x.clone();
-            let d_y = d_y.clone();
-            let weights = weights.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-                    Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-                } else {
-                    Box::new(|row: isize, col: isize| -> f64 {
-                        let value = input.get_value(row, col);
-                        if value != nodata {
-                            return value2i(value);
-                        }
-                        nodata
-                    })
-                };
-
-                let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
-                    // simply return the value.
-                    Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
-                } else {
-                    // convert it back into an rgb value, using the modified intensity value.
-                    Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
-                            let (h, s, _) = value2hsi(input.get_value(row, col));
-                            return hsi2value(h, s, value);
-                        }
-                        nodata
-                    })
-                };
-
-                let (mut sum, mut z_final): (f64, f64);
-                let mut z: f64;
-                let mut zn: f64;
-                let (mut x, mut y): (isize, isize);
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    let mut data = vec![nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input_fn(row, col);
-                        if z != nodata {
-                            sum = 0.0;
-                            z_final = 0.0;
-                            for a in 0..num_pixels_in_filter {
-                                x = col + d_x[a];
-                                y = row + d_y[a];
-                                zn = input_fn(y, x);
-                                if zn != nodata {
-                                    sum += weights[a];
-                                    z_final += weights[a] * zn;
-                                }
-                            }
-                            data[col as usize] = output_fn(row, col, z_final / sum);
-                        }
-                    }
-
-                    tx1.send((row, data)).unwrap();
-                }
-            });
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
-        for row in 0..rows {
-            let data = rx.recv().expect("Error receiving data from thread.");
-            output.set_row_data(data.0, data.1);
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
+        let conv_input = Arc::new(ConvolutionInput::new(
+            &input,
+            if is_rgb_image { Some(value2i) } else { None },
+        ));
+        let filtered = convolve(
+            &conv_input,
+            &kernel,
+            NodataMode::Normalize,
+            nodata,
+            num_procs,
+            verbose,
+            reporter,
+        )?;
+
+        for (row, mut data) in filtered.into_iter().enumerate() {
+            if is_rgb_image {
+                // convert it back into an rgb value, using the modified intensity value.
+                for col in 0..columns {
+                    if !is_nodata(data[col as usize], nodata) {
+                        let (h, s, _) = value2hsi(input.get_value(row as isize, col));
+                        data[col as usize] = hsi2value(h, s, data[col as usize]);
+                    }
                 }
             }
+            output.set_row_data(row as isize, data);
         }
 
         let elapsed_time = get_formatted_elapsed_time(start);
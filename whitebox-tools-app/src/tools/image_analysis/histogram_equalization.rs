@@ -12,7 +12,6 @@ NOTES: 1. The tool should be updated to take multiple file inputs.
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
@@ -20,7 +19,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool alters the cumulative distribution function (CDF) of a raster image to match,
 /// as closely as possible, the CDF of a uniform distribution. Histogram equalization works
@@ -154,53 +153,12 @@ impl WhiteboxTool for HistogramEqualization {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut num_tones = 256f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-num_tones" {
-                if keyval {
-                    num_tones = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    num_tones = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut num_tones = parsed.get_f64("num_tones").unwrap_or(256f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -213,17 +171,11 @@ impl WhiteboxTool for HistogramEqualization {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if num_tones < 16f64 {
             println!("Warning: The output number of greytones must be at least 16. The value has been modified.");
@@ -282,16 +234,17 @@ impl WhiteboxTool for HistogramEqualization {
             for row in 0..rows {
                 for col in 0..columns {
                     value = input.get_value(row, col);
-                    if value != nodata {
+                    if !is_nodata(value, nodata) {
                         n += 1f64;
                         bin = ((value - min_value) / bin_size).floor() as usize;
                         histo[bin] += 1f64;
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Calculating histogram: {}%", progress);
+                        reporter.progress("Calculating histogram", progress);
                         old_progress = progress;
                     }
                 }
@@ -306,17 +259,18 @@ impl WhiteboxTool for HistogramEqualization {
             for row in 0..rows {
                 for col in 0..columns {
                     value = input.get_value(row, col);
-                    if value != nodata {
+                    if !is_nodata(value, nodata) {
                         v = value2i(value);
                         n += 1f64;
                         bin = (v * (num_bins - 1) as f64).floor() as usize;
                         histo[bin] += 1f64;
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Calculating histogram: {}%", progress);
+                        reporter.progress("Calculating histogram", progress);
                         old_progress = progress;
                     }
                 }
@@ -332,18 +286,14 @@ impl WhiteboxTool for HistogramEqualization {
 
         let cdf = Arc::new(cdf); // wrap the cdf in an arc
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let cdf = cdf.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let input_fn: Box<dyn Fn(isize, isize) -> usize> = if !is_rgb_image {
                     Box::new(|row: isize, col: isize| -> usize {
                         let x = input.get_value(row, col);
@@ -361,7 +311,7 @@ impl WhiteboxTool for HistogramEqualization {
                     Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
                 } else {
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             // convert the value into an rgb value based on modified hsi values.
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, value / num_tones_less_one);
@@ -379,7 +329,7 @@ impl WhiteboxTool for HistogramEqualization {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z_in = input[(row, col)];
-                        if z_in != nodata {
+                        if !is_nodata(z_in, nodata) {
                             bin = input_fn(row, col);
                             z_out = ((cdf[bin] - min_nonempty_bin) / num_cells_less_one
                                 * num_tones_less_one)
@@ -387,7 +337,9 @@ impl WhiteboxTool for HistogramEqualization {
                             data[col as usize] = output_fn(row, col, z_out);
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -396,10 +348,11 @@ impl WhiteboxTool for HistogramEqualization {
         for r in 0..rows {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -8,7 +8,6 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs::File;
@@ -18,7 +17,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool alters the cumulative distribution function (CDF) of a raster image to match,
 /// as closely as possible, the CDF of a reference histogram. Histogram matching works by
@@ -158,51 +157,12 @@ impl WhiteboxTool for HistogramMatching {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut histo_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i"
-                || vec[0].to_lowercase() == "--i"
-                || vec[0].to_lowercase() == "--input"
-            {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-histo_file"
-                || vec[0].to_lowercase() == "--histo_file"
-            {
-                if keyval {
-                    histo_file = vec[1].to_string();
-                } else {
-                    histo_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut histo_file = parsed.get_string("histo_file").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -215,20 +175,12 @@ impl WhiteboxTool for HistogramMatching {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !histo_file.contains(&sep) && !histo_file.contains("/") {
-            histo_file = format!("{}{}", working_directory, histo_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        histo_file = resolve_input_path(&histo_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading input data...")
@@ -260,7 +212,7 @@ impl WhiteboxTool for HistogramMatching {
         for row in 0..rows {
             for col in 0..columns {
                 z = input[(row, col)];
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     numcells += 1f64;
                     bin_num = ((z - min_value) / bin_size) as usize;
                     if bin_num > num_bins_less_one {
@@ -269,10 +221,11 @@ impl WhiteboxTool for HistogramMatching {
                     histogram[bin_num] += 1f64;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Loop 1 of 2: {}%", progress);
+                    reporter.progress("Loop 1 of 2", progress);
                     old_progress = progress;
                 }
             }
@@ -292,6 +245,7 @@ impl WhiteboxTool for HistogramMatching {
         let f = File::open(histo_file.clone())?;
         let f = BufReader::new(f);
         for line in f.lines() {
+            reporter.check_cancelled()?;
             // Remove the utf-8 byte order mark, if it's there. Also, there should be no line returns
             // but under some circumstances, it may show up (e.g. Excel for Mac inserts \r instead of \n).
             let line_unwrapped = line.unwrap().replace("\u{feff}", "").replace("\r", ",");
@@ -381,12 +335,8 @@ impl WhiteboxTool for HistogramMatching {
         let reference_cdf = Arc::new(reference_cdf);
         let cdf = Arc::new(cdf);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
@@ -394,7 +344,7 @@ impl WhiteboxTool for HistogramMatching {
             let reference_cdf = reference_cdf.clone();
             let cdf = cdf.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut bin_num: usize;
                 let mut j: usize;
@@ -405,7 +355,7 @@ impl WhiteboxTool for HistogramMatching {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             bin_num = ((z - min_value) / bin_size) as usize;
                             if bin_num > num_bins_less_one {
                                 bin_num = num_bins_less_one;
@@ -433,7 +383,9 @@ impl WhiteboxTool for HistogramMatching {
                             data[col as usize] = x_val;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -442,10 +394,11 @@ impl WhiteboxTool for HistogramMatching {
         for r in 0..rows {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Loop 2 of 2: {}%", progress);
+                    reporter.progress("Loop 2 of 2", progress);
                     old_progress = progress;
                 }
             }
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool alters the cumulative distribution function (CDF) of a raster image to match, as closely
 /// as possible, the CDF of a reference image. Histogram matching works by first calculating the
@@ -142,52 +141,12 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1 = String::new();
-        let mut input_file2 = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i1"
-                || vec[0].to_lowercase() == "--i1"
-                || vec[0].to_lowercase() == "--input1"
-            {
-                if keyval {
-                    input_file1 = vec[1].to_string();
-                } else {
-                    input_file1 = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-i2"
-                || vec[0].to_lowercase() == "--i2"
-                || vec[0].to_lowercase() == "--input2"
-            {
-                if keyval {
-                    input_file2 = vec[1].to_string();
-                } else {
-                    input_file2 = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -200,20 +159,12 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading input data...")
@@ -256,7 +207,7 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
         for row in 0..rows1 {
             for col in 0..columns1 {
                 z = input1[(row, col)];
-                if z != nodata1 {
+                if !is_nodata(z, nodata1) {
                     numcells1 += 1f64;
                     bin_num = ((z - min_value1) / bin_size) as usize;
                     if bin_num > num_bins_less_one1 {
@@ -265,10 +216,11 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
                     histogram[bin_num] += 1f64;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows1 - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Loop 1 of 3: {}%", progress);
+                    reporter.progress("Loop 1 of 3", progress);
                     old_progress = progress;
                 }
             }
@@ -297,7 +249,7 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
         for row in 0..rows2 {
             for col in 0..columns2 {
                 z = input2[(row, col)];
-                if z != nodata2 {
+                if !is_nodata(z, nodata2) {
                     numcells2 += 1f64;
                     bin_num = ((z - min_value2) / bin_size) as usize;
                     if bin_num > num_bins_less_one2 {
@@ -306,10 +258,11 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
                     histogram2[bin_num] += 1f64;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows2 - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Loop 2 of 3: {}%", progress);
+                    reporter.progress("Loop 2 of 3", progress);
                     old_progress = progress;
                 }
             }
@@ -411,12 +364,8 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
         let reference_cdf = Arc::new(reference_cdf);
         let cdf = Arc::new(cdf);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input1 = input1.clone();
@@ -424,7 +373,7 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
             let reference_cdf = reference_cdf.clone();
             let cdf = cdf.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut bin_num: usize;
                 let mut j: usize;
@@ -435,7 +384,7 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
                     let mut data: Vec<f64> = vec![nodata1; columns1 as usize];
                     for col in 0..columns1 {
                         z = input1[(row, col)];
-                        if z != nodata1 {
+                        if !is_nodata(z, nodata1) {
                             bin_num = ((z - min_value1) / bin_size) as usize;
                             if bin_num > num_bins_less_one1 {
                                 bin_num = num_bins_less_one1;
@@ -463,7 +412,9 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
                             data[col as usize] = x_val;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -472,10 +423,11 @@ impl WhiteboxTool for HistogramMatchingTwoImages {
         for r in 0..rows1 {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows1 - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Loop 3 of 3: {}%", progress);
+                    reporter.progress("Loop 3 of 3", progress);
                     old_progress = progress;
                 }
             }
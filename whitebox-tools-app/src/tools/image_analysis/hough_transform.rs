@@ -0,0 +1,676 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::structures::Point2D;
+use whitebox_raster::*;
+use whitebox_vector::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::f64::consts::PI;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool uses the [Hough transform](https://en.wikipedia.org/wiki/Hough_transform) to detect
+/// straight linear features, such as agricultural drains, roads, and faults, or circular features,
+/// such as centre-pivot irrigation fields, in a binary edge raster (`--input`). All positive cells of
+/// the input are edge cells; an edge raster can be created from an image or a hillshade using, e.g.,
+/// `SobelFilter` followed by a threshold and `Skeletonize`. The detected features
+/// are written to a vector file (`--output`), as lines or as circular polygons depending on the
+/// `--feature` parameter.
+///
+/// **Lines**: Every edge cell votes for each of the lines through it, parameterized by the angle of
+/// the line's normal, at `--theta_res` degree intervals, and by the line's distance from the centre of
+/// the raster, at one-cell intervals. The local maxima of the votes that exceed `--min_votes` are
+/// visited in decreasing order of votes. The edge cells lying within one cell of each peak's line, and
+/// not already assigned to a stronger line, are sorted along the line and split wherever the gap between
+/// them exceeds `--max_gap` (in map units). Each resulting segment that is at least `--min_length` long
+/// is output as a line feature with the attributes `VOTES` (the number of edge cells in the segment),
+/// `AZIMUTH` (the direction of the line, from 0 to 180 degrees clockwise from north), and `LENGTH`.
+///
+/// **Circles**: For each radius between `--min_radius` and `--max_radius` (in map units, at one-cell
+/// intervals), every edge cell votes for the centres of the circles of that radius that pass through it.
+/// The score of a candidate circle is the fraction of the cells on its circumference that are edge
+/// cells. Local maxima with scores of at least `--min_score` are accepted in decreasing order of score,
+/// skipping those whose centres lie within the radius of an already accepted circle. Circles are output
+/// as polygons with the attributes `RADIUS`, `SCORE`, `CENTRE_X`, and `CENTRE_Y`.
+///
+/// For both feature types, at most `--max_features` features are output. The Hough transform assumes
+/// square grid cells.
+///
+/// # Reference
+/// Duda, R. O., & Hart, P. E. (1972). Use of the Hough transformation to detect lines and curves in
+/// pictures. *Communications of the ACM*, 15(1), 11-15.
+///
+/// # See Also
+/// `SobelFilter`, `Skeletonize`, `LineDetectionFilter`
+pub struct HoughTransform {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl HoughTransform {
+    pub fn new() -> HoughTransform {
+        // public constructor
+        let name = "HoughTransform".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Detects linear or circular features in a binary edge raster using the Hough transform.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input binary edge raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file of the detected lines or circles.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Feature Type".to_owned(),
+            flags: vec!["--feature".to_owned()],
+            description: "Type of feature to detect; either 'lines' or 'circles'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "lines".to_owned(),
+                "circles".to_owned(),
+            ]),
+            default_value: Some("lines".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Angular Resolution (degrees)".to_owned(),
+            flags: vec!["--theta_res".to_owned()],
+            description: "Angular resolution of the line accumulator, in degrees.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Line Votes".to_owned(),
+            flags: vec!["--min_votes".to_owned()],
+            description: "Minimum number of edge cells voting for a line.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("20".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Line Length (optional)".to_owned(),
+            flags: vec!["--min_length".to_owned()],
+            description: "Minimum length of an output line segment, in map units; defaults to 10 cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Line Gap (optional)".to_owned(),
+            flags: vec!["--max_gap".to_owned()],
+            description: "Maximum gap between the edge cells of a line segment, in map units; defaults to 2 cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Circle Radius".to_owned(),
+            flags: vec!["--min_radius".to_owned()],
+            description: "Minimum circle radius, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Circle Radius".to_owned(),
+            flags: vec!["--max_radius".to_owned()],
+            description: "Maximum circle radius, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Circle Score".to_owned(),
+            flags: vec!["--min_score".to_owned()],
+            description: "Minimum fraction (0-1) of a circle's circumference that must be edge cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Number of Features".to_owned(),
+            flags: vec!["--max_features".to_owned()],
+            description: "Maximum number of output features.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("100".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=edges.tif -o=drains.shp --feature=lines --min_votes=50 --min_length=100.0 --max_gap=20.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=edges.tif -o=pivots.shp --feature=circles --min_radius=200.0 --max_radius=500.0 --min_score=0.6",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        HoughTransform {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for HoughTransform {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut feature = String::from("lines");
+        let theta_res = parsed.get_f64("theta_res").unwrap_or(1f64);
+        let min_votes = parsed.get_usize("min_votes")?.unwrap_or(20usize);
+        let min_length = parsed.get_f64("min_length").unwrap_or(-1f64);
+        let max_gap = parsed.get_f64("max_gap").unwrap_or(-1f64);
+        let min_radius = parsed.get_f64("min_radius").unwrap_or(-1f64);
+        let max_radius = parsed.get_f64("max_radius").unwrap_or(-1f64);
+        let min_score = parsed.get_f64("min_score").unwrap_or(0.5f64);
+        let max_features = parsed.get_usize("max_features")?.unwrap_or(100usize);
+
+        if let Some(value) = parsed.get_string("feature") {
+            feature = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let detect_lines = if feature.contains("line") {
+            true
+        } else if feature.contains("circ") {
+            false
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --feature parameter must be either 'lines' or 'circles'.",
+            ));
+        };
+        if !detect_lines && (min_radius <= 0f64 || max_radius < min_radius) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Circle detection requires positive --min_radius and --max_radius values, with --max_radius >= --min_radius.",
+            ));
+        }
+        if theta_res <= 0f64 || theta_res > 90f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --theta_res parameter must be between 0 and 90 degrees.",
+            ));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let cell_size = input.configs.resolution_x;
+
+        let mut edges = vec![];
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if !is_nodata(z, nodata) && z > 0f64 {
+                    edges.push((row, col));
+                }
+            }
+        }
+        if verbose {
+            println!("Number of edge cells: {}", edges.len());
+        }
+
+        let shape_type = if detect_lines {
+            ShapeType::PolyLine
+        } else {
+            ShapeType::Polygon
+        };
+        let mut output = Shapefile::new(&output_file, shape_type)?;
+        output.projection = input.configs.coordinate_ref_system_wkt.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+
+        if detect_lines {
+            output
+                .attributes
+                .add_field(&AttributeField::new("VOTES", FieldDataType::Int, 9u8, 0u8));
+            output.attributes.add_field(&AttributeField::new(
+                "AZIMUTH",
+                FieldDataType::Real,
+                7u8,
+                2u8,
+            ));
+            output.attributes.add_field(&AttributeField::new(
+                "LENGTH",
+                FieldDataType::Real,
+                12u8,
+                3u8,
+            ));
+
+            // distances in cells
+            let min_length_cells = if min_length > 0f64 {
+                min_length / cell_size
+            } else {
+                10f64
+            };
+            let max_gap_cells = if max_gap >= 0f64 {
+                max_gap / cell_size
+            } else {
+                2f64
+            };
+
+            // The line x cos(theta) + y sin(theta) = rho, in cell coordinates relative to the
+            // centre of the raster, with y increasing downward.
+            let (cx, cy) = ((columns - 1) as f64 / 2f64, (rows - 1) as f64 / 2f64);
+            let num_theta = (180f64 / theta_res).round().max(1f64) as usize;
+            let d_theta = PI / num_theta as f64;
+            let max_rho = ((cx + 1f64) * (cx + 1f64) + (cy + 1f64) * (cy + 1f64)).sqrt();
+            let num_rho = (2f64 * max_rho).ceil() as usize + 1;
+            let (sin, cos): (Vec<f64>, Vec<f64>) = (0..num_theta)
+                .map(|t| ((t as f64 * d_theta).sin(), (t as f64 * d_theta).cos()))
+                .unzip();
+            let rho_bin = |rho: f64| (rho + max_rho).round() as usize;
+
+            let mut accum = vec![0u32; num_theta * num_rho];
+            for &(row, col) in &edges {
+                let (x, y) = (col as f64 - cx, row as f64 - cy);
+                for t in 0..num_theta {
+                    accum[t * num_rho + rho_bin(x * cos[t] + y * sin[t])] += 1;
+                }
+            }
+
+            // local maxima, where the neighbourhood wraps around theta with the sign of rho reversed
+            let mut peaks = vec![];
+            for t in 0..num_theta {
+                for r in 0..num_rho {
+                    let v = accum[t * num_rho + r];
+                    if (v as usize) < min_votes {
+                        continue;
+                    }
+                    let mut is_max = true;
+                    'nbrs: for dt in -2isize..=2 {
+                        for dr in -2isize..=2 {
+                            if dt == 0 && dr == 0 {
+                                continue;
+                            }
+                            let mut tn = t as isize + dt;
+                            let mut rn = r as isize + dr;
+                            if tn < 0 || tn >= num_theta as isize {
+                                tn = (tn + num_theta as isize) % num_theta as isize;
+                                rn = num_rho as isize - 1 - rn;
+                            }
+                            if rn < 0 || rn >= num_rho as isize {
+                                continue;
+                            }
+                            let vn = accum[tn as usize * num_rho + rn as usize];
+                            // ties are broken by the position in the accumulator
+                            if vn > v || (vn == v && (tn as usize, rn as usize) < (t, r)) {
+                                is_max = false;
+                                break 'nbrs;
+                            }
+                        }
+                    }
+                    if is_max {
+                        peaks.push((v, t, r));
+                    }
+                }
+            }
+            peaks.sort_by(|a, b| b.0.cmp(&a.0));
+            if verbose {
+                println!("Number of accumulator peaks: {}", peaks.len());
+            }
+
+            let mut assigned = vec![false; edges.len()];
+            let mut fid = 1i32;
+            'peaks: for &(_, t, r) in &peaks {
+                let rho = r as f64 - max_rho;
+                let (sin_t, cos_t) = (sin[t], cos[t]);
+                // the edge cells near the line, by position along it
+                let mut on_line = vec![];
+                for (i, &(row, col)) in edges.iter().enumerate() {
+                    if assigned[i] {
+                        continue;
+                    }
+                    let (x, y) = (col as f64 - cx, row as f64 - cy);
+                    if (x * cos_t + y * sin_t - rho).abs() <= 1f64 {
+                        on_line.push((-x * sin_t + y * cos_t, i));
+                    }
+                }
+                if on_line.len() < min_votes {
+                    continue;
+                }
+                on_line.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+                let mut first = 0;
+                for j in 1..=on_line.len() {
+                    if j < on_line.len() && on_line[j].0 - on_line[j - 1].0 <= max_gap_cells + 1f64 {
+                        continue;
+                    }
+                    // the segment from first to j - 1
+                    let (t0, t1) = (on_line[first].0, on_line[j - 1].0);
+                    let votes = j - first;
+                    if t1 - t0 + 1f64 >= min_length_cells && votes >= 2 {
+                        let mut points = vec![];
+                        for &s in &[t0, t1] {
+                            let x = rho * cos_t - s * sin_t + cx;
+                            let y = rho * sin_t + s * cos_t + cy;
+                            points.push(Point2D::new(
+                                input.configs.west + (x + 0.5) * input.configs.resolution_x,
+                                input.configs.north - (y + 0.5) * input.configs.resolution_y,
+                            ));
+                        }
+                        let (dx, dy) = (points[1].x - points[0].x, points[1].y - points[0].y);
+                        let mut azimuth = dx.atan2(dy).to_degrees();
+                        if azimuth < 0f64 {
+                            azimuth += 180f64;
+                        }
+                        if azimuth >= 180f64 {
+                            azimuth -= 180f64;
+                        }
+                        let length = (dx * dx + dy * dy).sqrt();
+                        let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+                        sfg.add_part(&points);
+                        output.add_record(sfg);
+                        output.attributes.add_record(
+                            vec![
+                                FieldData::Int(fid),
+                                FieldData::Int(votes as i32),
+                                FieldData::Real(azimuth),
+                                FieldData::Real(length),
+                            ],
+                            false,
+                        );
+                        fid += 1;
+                        for k in first..j {
+                            assigned[on_line[k].1] = true;
+                        }
+                        if fid as usize > max_features {
+                            break 'peaks;
+                        }
+                    }
+                    first = j;
+                }
+            }
+        } else {
+            output.attributes.add_field(&AttributeField::new(
+                "RADIUS",
+                FieldDataType::Real,
+                12u8,
+                3u8,
+            ));
+            output.attributes.add_field(&AttributeField::new(
+                "SCORE",
+                FieldDataType::Real,
+                6u8,
+                4u8,
+            ));
+            output.attributes.add_field(&AttributeField::new(
+                "CENTRE_X",
+                FieldDataType::Real,
+                16u8,
+                4u8,
+            ));
+            output.attributes.add_field(&AttributeField::new(
+                "CENTRE_Y",
+                FieldDataType::Real,
+                16u8,
+                4u8,
+            ));
+
+            let min_r = (min_radius / cell_size).round().max(1f64) as usize;
+            let max_r = (max_radius / cell_size).round().max(min_r as f64) as usize;
+            let edges = Arc::new(edges);
+            let configs = whitebox_common::configs::get_configs()?;
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+            let num_procs = (num_procs as usize).min(max_r - min_r + 1);
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let edges = edges.clone();
+                let tx = tx.clone();
+                spawn(move || {
+                    let mut accum = vec![0u32; (rows * columns) as usize];
+                    let mut r = min_r + tid;
+                    while r <= max_r {
+                        // the distinct cell offsets on the circle
+                        let num_samples = (8f64 * r as f64).ceil() as usize;
+                        let mut offsets: Vec<(isize, isize)> = (0..num_samples)
+                            .map(|k| {
+                                let a = 2f64 * PI * k as f64 / num_samples as f64;
+                                (
+                                    (r as f64 * a.sin()).round() as isize,
+                                    (r as f64 * a.cos()).round() as isize,
+                                )
+                            })
+                            .collect();
+                        offsets.sort();
+                        offsets.dedup();
+                        for v in accum.iter_mut() {
+                            *v = 0;
+                        }
+                        for &(row, col) in edges.iter() {
+                            for &(dr, dc) in &offsets {
+                                let (rn, cn) = (row + dr, col + dc);
+                                if rn >= 0 && rn < rows && cn >= 0 && cn < columns {
+                                    accum[(rn * columns + cn) as usize] += 1;
+                                }
+                            }
+                        }
+                        let n = offsets.len() as f64;
+                        let mut candidates = vec![];
+                        for row in 0..rows {
+                            for col in 0..columns {
+                                let v = accum[(row * columns + col) as usize];
+                                let score = v as f64 / n;
+                                if score < min_score {
+                                    continue;
+                                }
+                                let mut is_max = true;
+                                for dr in -1isize..=1 {
+                                    for dc in -1isize..=1 {
+                                        let (rn, cn) = (row + dr, col + dc);
+                                        if (dr != 0 || dc != 0)
+                                            && rn >= 0
+                                            && rn < rows
+                                            && cn >= 0
+                                            && cn < columns
+                                        {
+                                            let vn = accum[(rn * columns + cn) as usize];
+                                            if vn > v || (vn == v && (rn, cn) < (row, col)) {
+                                                is_max = false;
+                                            }
+                                        }
+                                    }
+                                }
+                                if is_max {
+                                    candidates.push((score, r, row, col));
+                                }
+                            }
+                        }
+                        if tx.send(candidates).is_err() {
+                            return;
+                        }
+                        r += num_procs;
+                    }
+                });
+            }
+
+            let mut candidates = vec![];
+            let num_radii = max_r - min_r + 1;
+            let mut progress: usize;
+            let mut old_progress: usize = 1;
+            for i in 0..num_radii {
+                candidates.extend(rx.recv().expect("Error receiving data from thread."));
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * (i + 1) as f64 / num_radii as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Accumulating circles", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap().then(a.1.cmp(&b.1)));
+
+            let mut accepted: Vec<(f64, f64, f64)> = vec![]; // (row, col, radius), in cells
+            let mut fid = 1i32;
+            for &(score, r, row, col) in &candidates {
+                let (rf, cf, radius) = (row as f64, col as f64, r as f64);
+                if accepted.iter().any(|&(ra, ca, rad)| {
+                    ((ra - rf) * (ra - rf) + (ca - cf) * (ca - cf)).sqrt() < rad.max(radius)
+                }) {
+                    continue;
+                }
+                accepted.push((rf, cf, radius));
+                let x = input.get_x_from_column(col);
+                let y = input.get_y_from_row(row);
+                let radius = radius * cell_size;
+                let num_vertices = 64;
+                let mut points = vec![];
+                for k in 0..=num_vertices {
+                    // clockwise from the north
+                    let a = 2f64 * PI * (k % num_vertices) as f64 / num_vertices as f64;
+                    points.push(Point2D::new(x + radius * a.sin(), y + radius * a.cos()));
+                }
+                let mut sfg = ShapefileGeometry::new(ShapeType::Polygon);
+                sfg.add_part(&points);
+                output.add_record(sfg);
+                output.attributes.add_record(
+                    vec![
+                        FieldData::Int(fid),
+                        FieldData::Real(radius),
+                        FieldData::Real(score),
+                        FieldData::Real(x),
+                        FieldData::Real(y),
+                    ],
+                    false,
+                );
+                fid += 1;
+                if fid as usize > max_features {
+                    break;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Number of features detected: {}", output.num_records);
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
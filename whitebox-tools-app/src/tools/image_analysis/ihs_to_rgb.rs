@@ -8,15 +8,14 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool transforms three intensity, hue, and saturation (IHS; sometimes HSI or HIS) raster images into three
 /// equivalent multispectral images corresponding with the red, green, and blue channels of an RGB composite. Intensity
@@ -66,7 +65,7 @@ impl IhsToRgb {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input Intensity File".to_owned(),
-            flags: vec!["--intensity".to_owned()],
+            flags: vec!["--i".to_owned(), "--intensity".to_owned()],
             description: "Input intensity file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -75,7 +74,7 @@ impl IhsToRgb {
 
         parameters.push(ToolParameter {
             name: "Input Hue File".to_owned(),
-            flags: vec!["--hue".to_owned()],
+            flags: vec!["--h".to_owned(), "--hue".to_owned()],
             description: "Input hue file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -84,7 +83,7 @@ impl IhsToRgb {
 
         parameters.push(ToolParameter {
             name: "Input Saturation File".to_owned(),
-            flags: vec!["--saturation".to_owned()],
+            flags: vec!["--s".to_owned(), "--saturation".to_owned()],
             description: "Input saturation file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -94,7 +93,7 @@ impl IhsToRgb {
         parameters.push(ToolParameter {
             name: "Output Red Band File (optional; only if colour-composite not specified)"
                 .to_owned(),
-            flags: vec!["--red".to_owned()],
+            flags: vec!["--r".to_owned(), "--red".to_owned()],
             description:
                 "Output red band file. Optionally specified if colour-composite not specified."
                     .to_owned(),
@@ -106,7 +105,7 @@ impl IhsToRgb {
         parameters.push(ToolParameter {
             name: "Output Green Band File (optional; only if colour-composite not specified)"
                 .to_owned(),
-            flags: vec!["--green".to_owned()],
+            flags: vec!["--g".to_owned(), "--green".to_owned()],
             description:
                 "Output green band file. Optionally specified if colour-composite not specified."
                     .to_owned(),
@@ -118,7 +117,7 @@ impl IhsToRgb {
         parameters.push(ToolParameter {
             name: "Output Blue Band File (optional; only if colour-composite not specified)"
                 .to_owned(),
-            flags: vec!["--blue".to_owned()],
+            flags: vec!["--b".to_owned(), "--blue".to_owned()],
             description:
                 "Output blue band file. Optionally specified if colour-composite not specified."
                     .to_owned(),
@@ -130,7 +129,7 @@ impl IhsToRgb {
         parameters.push(ToolParameter {
             name: "Output Colour-Composite File (optional; only if individual bands not specified)"
                 .to_owned(),
-            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            flags: vec!["-o".to_owned(), "--composite".to_owned(), "--output".to_owned()],
             description:
                 "Output colour-composite file. Only used if individual bands are not specified."
                     .to_owned(),
@@ -198,75 +197,20 @@ impl WhiteboxTool for IhsToRgb {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut red_file = String::new();
-        let mut green_file = String::new();
-        let mut blue_file = String::new();
-        let mut intensity_file = String::new();
-        let mut hue_file = String::new();
-        let mut saturation_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut red_file = parsed.get_string("red").unwrap_or_default();
+        let mut green_file = parsed.get_string("green").unwrap_or_default();
+        let mut blue_file = parsed.get_string("blue").unwrap_or_default();
+        let mut intensity_file = parsed.get_string("intensity").unwrap_or_default();
+        let mut hue_file = parsed.get_string("hue").unwrap_or_default();
+        let mut saturation_file = parsed.get_string("saturation").unwrap_or_default();
         let mut composite_file = String::new();
         let mut use_composite = false;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-r" || flag_val == "-red" {
-                if keyval {
-                    red_file = vec[1].to_string();
-                } else {
-                    red_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-g" || flag_val == "-green" {
-                if keyval {
-                    green_file = vec[1].to_string();
-                } else {
-                    green_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-b" || flag_val == "-blue" {
-                if keyval {
-                    blue_file = vec[1].to_string();
-                } else {
-                    blue_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-i" || flag_val == "-intensity" {
-                if keyval {
-                    intensity_file = vec[1].to_string();
-                } else {
-                    intensity_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-h" || flag_val == "-hue" {
-                if keyval {
-                    hue_file = vec[1].to_string();
-                } else {
-                    hue_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-s" || flag_val == "-saturation" {
-                if keyval {
-                    saturation_file = vec[1].to_string();
-                } else {
-                    saturation_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-composite" || flag_val == "-output" {
-                if keyval {
-                    composite_file = vec[1].to_string();
-                } else {
-                    composite_file = args[i + 1].to_string();
-                }
-                use_composite = true;
-            }
+        if let Some(value) = parsed.get_string("output") {
+            composite_file = value;
+            use_composite = true;
         }
 
         if verbose {
@@ -280,35 +224,19 @@ impl WhiteboxTool for IhsToRgb {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
         if !use_composite {
-            if !red_file.contains(&sep) && !red_file.contains("/") {
-                red_file = format!("{}{}", working_directory, red_file);
-            }
-            if !green_file.contains(&sep) && !green_file.contains("/") {
-                green_file = format!("{}{}", working_directory, green_file);
-            }
-            if !blue_file.contains(&sep) && !blue_file.contains("/") {
-                blue_file = format!("{}{}", working_directory, blue_file);
-            }
+            red_file = resolve_path(&red_file, working_directory);
+            green_file = resolve_path(&green_file, working_directory);
+            blue_file = resolve_path(&blue_file, working_directory);
         } else {
-            if !composite_file.contains(&sep) && !composite_file.contains("/") {
-                composite_file = format!("{}{}", working_directory, composite_file);
-            }
-        }
-        if !intensity_file.contains(&sep) && !intensity_file.contains("/") {
-            intensity_file = format!("{}{}", working_directory, intensity_file);
-        }
-        if !hue_file.contains(&sep) && !hue_file.contains("/") {
-            hue_file = format!("{}{}", working_directory, hue_file);
-        }
-        if !saturation_file.contains(&sep) && !saturation_file.contains("/") {
-            saturation_file = format!("{}{}", working_directory, saturation_file);
+            composite_file = resolve_path(&composite_file, working_directory);
         }
+        intensity_file = resolve_input_path(&intensity_file, working_directory)?;
+        hue_file = resolve_input_path(&hue_file, working_directory)?;
+        saturation_file = resolve_input_path(&saturation_file, working_directory)?;
 
         if verbose {
             println!("Reading intensity band data...")
@@ -335,26 +263,18 @@ impl WhiteboxTool for IhsToRgb {
         if input_i.configs.rows != input_h.configs.rows
             || input_i.configs.columns != input_h.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input_i, &input_h, verbose);
         if input_i.configs.rows != input_s.configs.rows
             || input_i.configs.columns != input_s.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input_i, &input_s, verbose);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         if !use_composite {
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
@@ -362,7 +282,7 @@ impl WhiteboxTool for IhsToRgb {
                 let input_h = input_h.clone();
                 let input_s = input_s.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     // let (mut r, mut g, mut b): (f64, f64, f64);
                     let (mut i, mut h, mut s): (f64, f64, f64);
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -373,7 +293,7 @@ impl WhiteboxTool for IhsToRgb {
                             i = input_i[(row, col)];
                             h = input_h[(row, col)];
                             s = input_s[(row, col)];
-                            if i != nodata_i && h != nodata_h && s != nodata_s {
+                            if !is_nodata(i, nodata_i) && !is_nodata(h, nodata_h) && !is_nodata(s, nodata_s) {
                                 let (r, g, b) = hsi2rgb(h, s, i);
 
                                 red_data[col as usize] = r as f64;
@@ -381,7 +301,9 @@ impl WhiteboxTool for IhsToRgb {
                                 blue_data[col as usize] = b as f64;
                             }
                         }
-                        tx.send((row, red_data, green_data, blue_data)).unwrap();
+                        if tx.send((row, red_data, green_data, blue_data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -403,10 +325,11 @@ impl WhiteboxTool for IhsToRgb {
                 output_r.set_row_data(data.0, data.1);
                 output_g.set_row_data(data.0, data.2);
                 output_b.set_row_data(data.0, data.3);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -500,7 +423,7 @@ impl WhiteboxTool for IhsToRgb {
                 let input_h = input_h.clone();
                 let input_s = input_s.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut i, mut h, mut s): (f64, f64, f64);
                     let mut value: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -509,12 +432,14 @@ impl WhiteboxTool for IhsToRgb {
                             i = input_i[(row, col)];
                             h = input_h[(row, col)];
                             s = input_s[(row, col)];
-                            if i != nodata_i && h != nodata_h && s != nodata_s {
+                            if !is_nodata(i, nodata_i) && !is_nodata(h, nodata_h) && !is_nodata(s, nodata_s) {
                                 value = hsi2value(h, s, i);
                                 data[col as usize] = value;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -526,10 +451,11 @@ impl WhiteboxTool for IhsToRgb {
             for row in 0..rows {
                 let data = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(data.0, data.1);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -52,7 +52,7 @@ impl ImageStackProfile {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input Files".to_owned(),
-            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            flags: vec!["-i".to_owned(), "--input".to_owned(), "--inputs".to_owned()],
             description: "Input multispectral image files.".to_owned(),
             parameter_type: ParameterType::FileList(ParameterFileType::Raster),
             default_value: None,
@@ -144,47 +144,12 @@ impl WhiteboxTool for ImageStackProfile {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files_str = String::new();
-        let mut points_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" || flag_val == "-input" {
-                input_files_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-points" {
-                points_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files_str = parsed.get_string("inputs").unwrap_or_default();
+        let mut points_file = parsed.get_string("points").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -197,8 +162,6 @@ impl WhiteboxTool for ImageStackProfile {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -214,13 +177,9 @@ impl WhiteboxTool for ImageStackProfile {
                 "There is something incorrect about the input files. At least two inputs are required to operate this tool."));
         }
 
-        if !points_file.contains(&sep) && !points_file.contains("/") {
-            points_file = format!("{}{}", working_directory, points_file);
-        }
+        points_file = resolve_input_path(&points_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         if !output_file.ends_with(".html") {
             output_file.push_str(".html");
@@ -258,9 +217,7 @@ impl WhiteboxTool for ImageStackProfile {
         for i in 0..num_files {
             if !input_files[i].trim().is_empty() {
                 let mut input_file = input_files[i].trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
 
                 let image = Raster::new(&input_file, "r")?;
                 let nodata = image.configs.nodata;
@@ -274,7 +231,7 @@ impl WhiteboxTool for ImageStackProfile {
                     row = image.get_row_from_y(record.points[0].y);
                     col = image.get_column_from_x(record.points[0].x);
                     z = image.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         xdata[record_num][i] = (i + 1) as f64;
                         ydata[record_num][i] = z;
                     } else {
@@ -285,10 +242,11 @@ impl WhiteboxTool for ImageStackProfile {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / (num_files - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -351,6 +309,7 @@ impl WhiteboxTool for ImageStackProfile {
         writer.write("<tr>".as_bytes()).unwrap();
         writer.write("<th>Image</th>".as_bytes()).unwrap();
         for record_num in 0..num_points {
+            reporter.check_cancelled()?;
             writer
                 .write(&format!("<th>Point {}</th>", record_num + 1).as_bytes())
                 .unwrap();
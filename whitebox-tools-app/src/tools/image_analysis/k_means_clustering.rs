@@ -10,7 +10,6 @@ use whitebox_raster::*;
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use crate::tools::*;
-use num_cpus;
 use rand::prelude::*;
 use std::env;
 use std::f64;
@@ -22,7 +21,7 @@ use std::path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to perform a k-means clustering operation on two or more input
 /// images (`--inputs`), typically several bands of multi-spectral satellite imagery. The
@@ -111,7 +110,7 @@ impl KMeansClustering {
             description: "Minimum percent of cells changed between iterations before completion"
                 .to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("2.0".to_owned()),
+            default_value: Some("5.0".to_owned()),
             optional: true,
         });
 
@@ -194,110 +193,20 @@ impl WhiteboxTool for KMeansClustering {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files_str = String::new();
-        let mut output_file = String::new();
-        let mut output_html_file = String::new();
-        let mut num_classes = 0usize;
-        let mut max_iterations = 10usize;
-        let mut percent_changed_threshold = 5f64;
-        let mut initialization_mode = 1;
-        let mut min_class_size = 10;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                input_files_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_html" {
-                output_html_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-classes" {
-                num_classes = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-max_iterations" {
-                max_iterations = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-class_change" {
-                percent_changed_threshold = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-initialize" {
-                if keyval {
-                    if vec[1].to_string().to_lowercase().contains("rand") {
-                        initialization_mode = 0;
-                    }
-                } else {
-                    if args[i + 1].to_string().to_lowercase().contains("diag") {
-                        initialization_mode = 1;
-                    }
-                }
-            } else if flag_val == "-min_class_size" {
-                min_class_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files_str = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut output_html_file = parsed.get_string("out_html").unwrap_or_default();
+        let num_classes = parsed.get_usize("classes")?.unwrap_or(0usize);
+        let max_iterations = parsed.get_usize("max_iterations")?.unwrap_or(10usize);
+        let percent_changed_threshold = parsed.get_f64("class_change").unwrap_or(5f64);
+        let initialization_mode = match parsed.get_string("initialize") {
+            Some(value) if value.to_lowercase().contains("rand") => 0,
+            _ => 1,
+        };
+        let min_class_size = parsed.get_usize("min_class_size")?.unwrap_or(10usize);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -310,18 +219,12 @@ impl WhiteboxTool for KMeansClustering {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
-        if !output_html_file.contains(&sep) {
-            output_html_file = format!("{}{}", working_directory, output_html_file);
-        }
+        output_html_file = resolve_path(&output_html_file, working_directory);
 
         if !output_html_file.ends_with(".html") && !output_html_file.is_empty() {
             output_html_file.push_str(".html");
@@ -369,9 +272,7 @@ impl WhiteboxTool for KMeansClustering {
             }
             if !input_files[i].trim().is_empty() {
                 let mut input_file = input_files[i].trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 input_raster.push(Raster::new(&input_file, "r")?);
                 nodata.push(input_raster[i].configs.nodata);
                 minimum.push(input_raster[i].configs.minimum);
@@ -396,8 +297,7 @@ impl WhiteboxTool for KMeansClustering {
                     if input_raster[i].configs.rows as isize != rows
                         || input_raster[i].configs.columns as isize != columns
                     {
-                        return Err(Error::new(ErrorKind::InvalidInput,
-                            "All input images must share the same dimensions (rows and columns) and spatial extent."));
+                        return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
                     }
                 }
             }
@@ -459,12 +359,8 @@ impl WhiteboxTool for KMeansClustering {
 
             let mut cells_changed = 0f64;
 
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let centres = Arc::new(class_centres.clone());
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
@@ -472,7 +368,7 @@ impl WhiteboxTool for KMeansClustering {
                 let centres = centres.clone();
                 let nodata = nodata.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data = vec![-1isize; columns as usize];
                         let mut is_valid_data: bool;
@@ -486,7 +382,7 @@ impl WhiteboxTool for KMeansClustering {
                             is_valid_data = true;
                             for i in 0..num_files {
                                 value[i] = input_raster[i].get_value(row, col);
-                                if value[i] == nodata[i] {
+                                if is_nodata(value[i], nodata[i]) {
                                     is_valid_data = false;
                                     break;
                                 }
@@ -519,8 +415,9 @@ impl WhiteboxTool for KMeansClustering {
                                 }
                             }
                         }
-                        tx.send((row, data, class_centre_data, class_min, class_max))
-                            .unwrap();
+                        if tx.send((row, data, class_centre_data, class_min, class_max)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -536,7 +433,7 @@ impl WhiteboxTool for KMeansClustering {
                         which_class = data[col as usize] as usize;
                         z = output.get_value(row, col);
                         class = z as usize - 1usize;
-                        if z == out_nodata || which_class != class {
+                        if is_nodata(z, out_nodata) || which_class != class {
                             cells_changed += 1f64;
                             output.set_value(row, col, which_class as f64 + 1f64);
                         }
@@ -557,15 +454,11 @@ impl WhiteboxTool for KMeansClustering {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Progress (loop {} of {}): {}%",
-                            loop_num + 1,
-                            max_iterations,
-                            progress
-                        );
+                        reporter.progress(&format!("Progress (loop {} of {})", loop_num + 1, max_iterations), progress);
                         old_progress = progress;
                     }
                 }
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to perform one of four 3x3 line-detection filters on a raster image. These
 /// filters can be used to find one-cell-thick vertical, horizontal, or angled (135-degrees or
@@ -182,75 +181,31 @@ impl WhiteboxTool for LineDetectionFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
 
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut variant = "v".to_string();
-        let mut absvals = false;
+        let absvals = parsed.get_bool("absvals");
         let mut clip_amount = 0.0;
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_string("variant") {
+            variant = value;
+            if variant.to_lowercase().contains("v") {
+                variant = "v".to_string();
+            } else if variant.to_lowercase().contains("h") {
+                variant = "h".to_string();
+            } else if variant.to_lowercase().contains("45") {
+                variant = "45".to_string();
+            } else if variant.to_lowercase().contains("135") {
+                variant = "135".to_string();
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-variant" {
-                if keyval {
-                    variant = vec[1].to_string();
-                } else {
-                    variant = args[i + 1].to_string();
-                }
-                if variant.to_lowercase().contains("v") {
-                    variant = "v".to_string();
-                } else if variant.to_lowercase().contains("h") {
-                    variant = "h".to_string();
-                } else if variant.to_lowercase().contains("45") {
-                    variant = "45".to_string();
-                } else if variant.to_lowercase().contains("135") {
-                    variant = "135".to_string();
-                }
-            } else if flag_val == "-absvals" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    absvals = true;
-                }
-            } else if flag_val == "-clip" {
-                if keyval {
-                    clip_amount = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    clip_amount = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-                if clip_amount < 0.0 {
-                    clip_amount = 0.0;
-                }
+        }
+        if let Some(value) = parsed.get_f64("clip") {
+            clip_amount = value;
+            if clip_amount < 0.0 {
+                clip_amount = 0.0;
             }
         }
 
@@ -265,14 +220,8 @@ impl WhiteboxTool for LineDetectionFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut progress: usize;
         let mut old_progress: usize = 1;
@@ -291,18 +240,14 @@ impl WhiteboxTool for LineDetectionFilter {
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let variant = variant.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut sum: f64;
                 let mut z: f64;
                 let mut zn: f64;
@@ -323,11 +268,11 @@ impl WhiteboxTool for LineDetectionFilter {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 sum = 0.0;
                                 for i in 0..num_pixels_in_filter {
                                     zn = input[(row + dy[i], col + dx[i])];
-                                    if zn == nodata {
+                                    if is_nodata(zn, nodata) {
                                         zn = z; // replace it with z
                                     }
                                     sum += zn * weights[i];
@@ -335,18 +280,20 @@ impl WhiteboxTool for LineDetectionFilter {
                                 data[col as usize] = sum;
                             }
                         }
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 } else {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 sum = 0.0;
                                 for i in 0..num_pixels_in_filter {
                                     zn = input[(row + dy[i], col + dx[i])];
-                                    if zn == nodata {
+                                    if is_nodata(zn, nodata) {
                                         zn = z; // replace it with z
                                     }
                                     sum += zn * weights[i];
@@ -354,7 +301,9 @@ impl WhiteboxTool for LineDetectionFilter {
                                 data[col as usize] = sum.abs();
                             }
                         }
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -363,10 +312,11 @@ impl WhiteboxTool for LineDetectionFilter {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
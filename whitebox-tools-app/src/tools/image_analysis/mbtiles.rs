@@ -0,0 +1,364 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+// The MBTiles writer used by the ExportWebTiles tool. MBTiles files are SQLite databases, which
+// are written here directly in the SQLite file format (https://www.sqlite.org/fileformat.html),
+// without a database library. Only what an MBTiles file needs is written: a metadata table and a
+// tiles table, each a table b-tree that is built bottom-up as its rows are added.
+
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::{BufWriter, Error, SeekFrom};
+
+const PAGE_SIZE: usize = 4096;
+// the application ID of MBTiles databases, 'MPBX'
+const APPLICATION_ID: u32 = 0x4d50_4258;
+
+/// Writes an MBTiles file. Tiles are added in any order with `add_tile` and the file is complete
+/// once `finish` is called.
+pub(super) struct MbTilesWriter {
+    file: BufWriter<File>,
+    num_pages: u32,
+    tiles: TableBuilder,
+}
+
+impl MbTilesWriter {
+    pub(super) fn create(file_name: &str) -> Result<MbTilesWriter, Error> {
+        let mut file = BufWriter::new(File::create(file_name)?);
+        // Page 1, which holds the database header and schema, is written last.
+        file.write_all(&[0u8; PAGE_SIZE])?;
+        Ok(MbTilesWriter {
+            file,
+            num_pages: 1,
+            tiles: TableBuilder::new(),
+        })
+    }
+
+    /// Adds a tile, given its row in the TMS scheme, i.e. counted from the bottom of the grid.
+    pub(super) fn add_tile(&mut self, zoom: u32, column: u32, row: u32, data: &[u8]) -> Result<(), Error> {
+        let record = encode_record(&[
+            Value::Integer(zoom as i64),
+            Value::Integer(column as i64),
+            Value::Integer(row as i64),
+            Value::Blob(data),
+        ]);
+        let mut pages = Pages {
+            file: &mut self.file,
+            num_pages: &mut self.num_pages,
+        };
+        self.tiles.add_row(&mut pages, &record)
+    }
+
+    /// Writes the metadata table, given as name-value pairs, and completes the file.
+    pub(super) fn finish(mut self, metadata: &[(String, String)]) -> Result<(), Error> {
+        let mut pages = Pages {
+            file: &mut self.file,
+            num_pages: &mut self.num_pages,
+        };
+        let tiles_root = self.tiles.finish(&mut pages)?;
+        let mut metadata_table = TableBuilder::new();
+        for (name, value) in metadata {
+            let record = encode_record(&[Value::Text(name), Value::Text(value)]);
+            metadata_table.add_row(&mut pages, &record)?;
+        }
+        let metadata_root = metadata_table.finish(&mut pages)?;
+
+        // the schema table, sqlite_schema, on page 1
+        let schema = [
+            encode_record(&[
+                Value::Text("table"),
+                Value::Text("metadata"),
+                Value::Text("metadata"),
+                Value::Integer(metadata_root as i64),
+                Value::Text("CREATE TABLE metadata (name text, value text)"),
+            ]),
+            encode_record(&[
+                Value::Text("table"),
+                Value::Text("tiles"),
+                Value::Text("tiles"),
+                Value::Integer(tiles_root as i64),
+                Value::Text("CREATE TABLE tiles (zoom_level integer, tile_column integer, tile_row integer, tile_data blob)"),
+            ]),
+        ];
+        let cells: Vec<Vec<u8>> = schema
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let mut cell = vec![];
+                put_varint(&mut cell, record.len() as u64);
+                put_varint(&mut cell, i as u64 + 1);
+                cell.extend_from_slice(record);
+                cell
+            })
+            .collect();
+        let mut page = leaf_page(&cells, 100);
+        page[..100].copy_from_slice(&database_header(*pages.num_pages));
+        self.file.seek(SeekFrom::Start(0))?;
+        self.file.write_all(&page)?;
+        self.file.flush()
+    }
+}
+
+/// Returns the 100-byte database header at the start of page 1.
+fn database_header(num_pages: u32) -> [u8; 100] {
+    let mut h = [0u8; 100];
+    h[..16].copy_from_slice(b"SQLite format 3\0");
+    h[16..18].copy_from_slice(&(PAGE_SIZE as u16).to_be_bytes());
+    h[18] = 1; // legacy (rollback journal) file format write and read versions
+    h[19] = 1;
+    h[21] = 64; // maximum and minimum embedded payload fractions and leaf payload fraction
+    h[22] = 32;
+    h[23] = 32;
+    h[24..28].copy_from_slice(&1u32.to_be_bytes()); // file change counter
+    h[28..32].copy_from_slice(&num_pages.to_be_bytes());
+    h[40..44].copy_from_slice(&1u32.to_be_bytes()); // schema cookie
+    h[44..48].copy_from_slice(&4u32.to_be_bytes()); // schema format number
+    h[56..60].copy_from_slice(&1u32.to_be_bytes()); // UTF-8 text encoding
+    h[68..72].copy_from_slice(&APPLICATION_ID.to_be_bytes());
+    h[92..96].copy_from_slice(&1u32.to_be_bytes()); // version-valid-for number
+    h[96..100].copy_from_slice(&3_040_000u32.to_be_bytes()); // SQLite version number
+    h
+}
+
+/// Allocates and writes the pages of the database after page 1.
+struct Pages<'a> {
+    file: &'a mut BufWriter<File>,
+    num_pages: &'a mut u32,
+}
+
+impl<'a> Pages<'a> {
+    fn write(&mut self, page: &[u8]) -> Result<u32, Error> {
+        *self.num_pages += 1;
+        self.file.seek(SeekFrom::Start((*self.num_pages as u64 - 1) * PAGE_SIZE as u64))?;
+        self.file.write_all(page)?;
+        Ok(*self.num_pages)
+    }
+}
+
+/// Builds a table b-tree from rows added in order of their rowids, which are numbered from one.
+/// Rows are packed into leaf pages, which are written as they fill, and the interior pages are
+/// written by `finish`.
+struct TableBuilder {
+    cells: Vec<Vec<u8>>,
+    cells_size: usize,
+    num_rows: u64,
+    // the page number and largest rowid of each leaf page
+    leaves: Vec<(u32, u64)>,
+}
+
+impl TableBuilder {
+    fn new() -> TableBuilder {
+        TableBuilder {
+            cells: vec![],
+            cells_size: 0,
+            num_rows: 0,
+            leaves: vec![],
+        }
+    }
+
+    fn add_row(&mut self, pages: &mut Pages, record: &[u8]) -> Result<(), Error> {
+        self.num_rows += 1;
+        // The payload beyond that stored in the leaf page spills onto a chain of overflow pages.
+        let payload_size = record.len();
+        let local_size = local_payload_size(payload_size);
+        let mut cell = vec![];
+        put_varint(&mut cell, payload_size as u64);
+        put_varint(&mut cell, self.num_rows);
+        cell.extend_from_slice(&record[..local_size]);
+        if local_size < payload_size {
+            let first_overflow = write_overflow_pages(pages, &record[local_size..])?;
+            cell.extend_from_slice(&first_overflow.to_be_bytes());
+        }
+        if 8 + 2 * (self.cells.len() + 1) + self.cells_size + cell.len() > PAGE_SIZE {
+            self.write_leaf(pages)?;
+        }
+        self.cells_size += cell.len();
+        self.cells.push(cell);
+        Ok(())
+    }
+
+    fn write_leaf(&mut self, pages: &mut Pages) -> Result<(), Error> {
+        let page_num = pages.write(&leaf_page(&self.cells, 0))?;
+        self.leaves.push((page_num, self.num_rows - 1));
+        self.cells.clear();
+        self.cells_size = 0;
+        Ok(())
+    }
+
+    /// Writes the remaining pages of the table and returns its root page number.
+    fn finish(mut self, pages: &mut Pages) -> Result<u32, Error> {
+        if !self.cells.is_empty() || self.leaves.is_empty() {
+            let page_num = pages.write(&leaf_page(&self.cells, 0))?;
+            self.leaves.push((page_num, self.num_rows));
+        }
+        let mut level = self.leaves;
+        while level.len() > 1 {
+            let mut parents = vec![];
+            let mut children: Vec<(u32, u64)> = vec![];
+            let mut size = 12;
+            for child in level {
+                // a cell of an interior page is a child page number and a varint rowid key
+                let cell_size = 4 + varint_len(child.1);
+                if !children.is_empty() && size + 2 + cell_size > PAGE_SIZE {
+                    parents.push(write_interior_page(pages, &children)?);
+                    children.clear();
+                    size = 12;
+                }
+                size += 2 + cell_size;
+                children.push(child);
+            }
+            parents.push(write_interior_page(pages, &children)?);
+            level = parents;
+        }
+        Ok(level[0].0)
+    }
+}
+
+/// Writes an interior table page whose last child is its right-most pointer, and returns the
+/// page number and largest rowid of the page.
+fn write_interior_page(pages: &mut Pages, children: &[(u32, u64)]) -> Result<(u32, u64), Error> {
+    let (right_most, max_rowid) = children[children.len() - 1];
+    let cells: Vec<Vec<u8>> = children[..children.len() - 1]
+        .iter()
+        .map(|&(page_num, rowid)| {
+            let mut cell = page_num.to_be_bytes().to_vec();
+            put_varint(&mut cell, rowid);
+            cell
+        })
+        .collect();
+    let mut page = vec![0u8; PAGE_SIZE];
+    let content_start = write_cells(&mut page, 12, &cells);
+    page[0] = 0x05;
+    page[3..5].copy_from_slice(&(cells.len() as u16).to_be_bytes());
+    page[5..7].copy_from_slice(&(content_start as u16).to_be_bytes());
+    page[8..12].copy_from_slice(&right_most.to_be_bytes());
+    Ok((pages.write(&page)?, max_rowid))
+}
+
+/// Returns a leaf table page holding some cells, with its b-tree header at `offset`, which is
+/// non-zero only for page 1.
+fn leaf_page(cells: &[Vec<u8>], offset: usize) -> Vec<u8> {
+    let mut page = vec![0u8; PAGE_SIZE];
+    let content_start = write_cells(&mut page, offset + 8, cells);
+    page[offset] = 0x0D;
+    page[offset + 3..offset + 5].copy_from_slice(&(cells.len() as u16).to_be_bytes());
+    page[offset + 5..offset + 7].copy_from_slice(&(content_start as u16).to_be_bytes());
+    page
+}
+
+/// Writes cells at the end of a page and their pointers after its b-tree header, which ends at
+/// `pointers_start`, and returns the start of the cell content area.
+fn write_cells(page: &mut [u8], pointers_start: usize, cells: &[Vec<u8>]) -> usize {
+    let mut content_start = PAGE_SIZE;
+    for (i, cell) in cells.iter().enumerate() {
+        content_start -= cell.len();
+        page[content_start..content_start + cell.len()].copy_from_slice(cell);
+        let p = pointers_start + 2 * i;
+        page[p..p + 2].copy_from_slice(&(content_start as u16).to_be_bytes());
+    }
+    content_start
+}
+
+/// Writes the part of a payload that overflows its cell to a chain of overflow pages and returns
+/// the first page number of the chain. Each page begins with the number of the next page in the
+/// chain, or zero for the last page.
+fn write_overflow_pages(pages: &mut Pages, data: &[u8]) -> Result<u32, Error> {
+    let chunks: Vec<&[u8]> = data.chunks(PAGE_SIZE - 4).collect();
+    let first = *pages.num_pages + 1;
+    for (i, chunk) in chunks.iter().enumerate() {
+        let mut page = vec![0u8; PAGE_SIZE];
+        let next = if i + 1 < chunks.len() { first + i as u32 + 1 } else { 0 };
+        page[..4].copy_from_slice(&next.to_be_bytes());
+        page[4..4 + chunk.len()].copy_from_slice(chunk);
+        pages.write(&page)?;
+    }
+    Ok(first)
+}
+
+/// Returns the number of bytes of a payload that are stored in a table leaf cell, following the
+/// rules of the SQLite file format.
+fn local_payload_size(payload_size: usize) -> usize {
+    let usable = PAGE_SIZE;
+    let max_local = usable - 35;
+    if payload_size <= max_local {
+        return payload_size;
+    }
+    let min_local = (usable - 12) * 32 / 255 - 23;
+    let k = min_local + (payload_size - min_local) % (usable - 4);
+    if k <= max_local {
+        k
+    } else {
+        min_local
+    }
+}
+
+enum Value<'a> {
+    Integer(i64),
+    Text(&'a str),
+    Blob(&'a [u8]),
+}
+
+/// Encodes a row as an SQLite record: a header of the serial types of its values followed by the
+/// values.
+fn encode_record(values: &[Value]) -> Vec<u8> {
+    let mut types = vec![];
+    let mut body = vec![];
+    for value in values {
+        match value {
+            Value::Integer(v) => {
+                // 64-bit integers, serial type 6, keep the encoding simple
+                put_varint(&mut types, 6);
+                body.extend_from_slice(&v.to_be_bytes());
+            }
+            Value::Text(s) => {
+                put_varint(&mut types, 2 * s.len() as u64 + 13);
+                body.extend_from_slice(s.as_bytes());
+            }
+            Value::Blob(b) => {
+                put_varint(&mut types, 2 * b.len() as u64 + 12);
+                body.extend_from_slice(b);
+            }
+        }
+    }
+    // The header size includes the varint that encodes it, a single byte for a record of a few
+    // values.
+    let header_size = types.len() + 1;
+    let mut record = vec![];
+    put_varint(&mut record, header_size as u64);
+    record.extend_from_slice(&types);
+    record.extend_from_slice(&body);
+    record
+}
+
+/// Appends an SQLite variable-length integer: big-endian groups of seven bits, with the high bit
+/// of each byte set if another follows, except that a ninth byte holds eight bits.
+fn put_varint(buf: &mut Vec<u8>, v: u64) {
+    if v > 0x00ff_ffff_ffff_ffff {
+        for i in (1..9).rev() {
+            buf.push(((v >> (8 + 7 * (i - 1))) as u8 & 0x7f) | 0x80);
+        }
+        buf.push(v as u8);
+        return;
+    }
+    let n = varint_len(v);
+    for i in (0..n).rev() {
+        let byte = ((v >> (7 * i)) & 0x7f) as u8;
+        buf.push(if i > 0 { byte | 0x80 } else { byte });
+    }
+}
+
+fn varint_len(v: u64) -> usize {
+    if v > 0x00ff_ffff_ffff_ffff {
+        return 9;
+    }
+    let mut n = 1;
+    while v >> (7 * n) != 0 {
+        n += 1;
+    }
+    n
+}
@@ -2,22 +2,18 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 25/06/2017
-Last Modified: 22/10/2019
+Last Modified: 16/10/2026
 License: MIT
 */
 
+use super::convolution::{convolve, ConvolutionInput, Kernel, NodataMode};
 use whitebox_raster::*;
-use whitebox_common::structures::Array2D;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::i32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
-use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 // use tools::ParameterFileType;
 // use tools::ParameterType;
 // use tools::ToolParameter;
@@ -25,8 +21,8 @@ use crate::tools::*;
 
 /// This tool performs a mean filter operation on a raster image. A mean filter, a type of low-pass filter, can be
 /// used to emphasize the longer-range variability in an image, effectively acting to smooth the image. This can be
-/// useful for reducing the noise in an image. This tool utilizes an integral image approach (Crow, 1984) to ensure highly
-/// efficient filtering that is invariant to filter size. The algorithm operates by calculating the average value
+/// useful for reducing the noise in an image. This tool applies the filter separably, using running sums in the
+/// manner of summed-area tables (Crow, 1984), to ensure highly efficient filtering that is invariant to filter size. The algorithm operates by calculating the average value
 /// in a moving window centred on each grid cell.  Neighbourhood size, or filter size, is specified in the x and y
 /// dimensions using the `--filterx` and `--filtery` flags. These dimensions should be odd, positive integer values,
 /// e.g. 3, 5, 7, 9... If the kernel filter size is the same in the x and y dimensions, the silent `--filter` flag
@@ -42,6 +38,9 @@ use crate::tools::*;
 /// channel. NoData values in the input image are ignored during filtering. NoData values are assigned to all sites beyond
 /// the raster.
 ///
+/// Large images may be filtered on the GPU, in single precision, by running the tool with the
+/// `--compute=gpu` flag in a build of WhiteboxTools with the `gpu` feature.
+///
 /// # Reference
 /// Crow, F. C. (1984, January). Summed-area tables for texture mapping. In ACM SIGGRAPH computer graphics (Vol. 18, No.
 /// 3, pp. 207-212). ACM.
@@ -158,77 +157,16 @@ impl WhiteboxTool for MeanFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size_x = 3usize;
-        let mut filter_size_y = 3usize;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-filter" {
-                filter_size_x = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-                filter_size_y = filter_size_x;
-            } else if flag_val == "-filterx" {
-                filter_size_x = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-filtery" {
-                filter_size_y = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size_x = parsed.get_usize("filterx")?.unwrap_or(3usize);
+        let mut filter_size_y = parsed.get_usize("filtery")?.unwrap_or(3usize);
+        if let Some(value) = parsed.get_silent_f64("filter")? {
+            filter_size_x = value as usize;
+            filter_size_y = filter_size_x;
         }
 
         if verbose {
@@ -242,8 +180,6 @@ impl WhiteboxTool for MeanFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         if filter_size_x < 3 {
             filter_size_x = 3;
         }
@@ -259,17 +195,8 @@ impl WhiteboxTool for MeanFilter {
             filter_size_y += 1;
         }
 
-        let midpoint_x = (filter_size_x as f64 / 2f64).floor() as isize;
-        let midpoint_y = (filter_size_y as f64 / 2f64).floor() as isize;
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -279,7 +206,6 @@ impl WhiteboxTool for MeanFilter {
 
         let start = Instant::now();
 
-        let rows = input.configs.rows as isize;
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
 
@@ -292,160 +218,39 @@ impl WhiteboxTool for MeanFilter {
             false
         };
 
-        let min_val = if !is_rgb_image {
-            input.configs.minimum
-        } else {
-            0f64
-        };
-
-        // create the integral images
-        let mut integral: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
-        let mut integral_n: Array2D<i32> = Array2D::new(rows, columns, 0, -1)?;
-
-        let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-            Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-        } else {
-            Box::new(|row: isize, col: isize| -> f64 {
-                let value = input.get_value(row, col);
-                if value != nodata {
-                    return value2i(value);
-                }
-                nodata
-            })
-        };
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
-        let mut val: f64;
-        let mut sum: f64;
-        let mut sum_n: i32;
-        let mut i_prev: f64;
-        let mut n_prev: i32;
-        for row in 0..rows {
-            sum = 0f64;
-            sum_n = 0;
-            for col in 0..columns {
-                val = input_fn(row, col);
-                if val == nodata {
-                    val = 0f64;
-                } else {
-                    val -= min_val;
-                    sum_n += 1;
-                }
-                sum += val;
-                if row > 0 {
-                    i_prev = integral[(row - 1, col)];
-                    n_prev = integral_n[(row - 1, col)];
-                    integral[(row, col)] = sum + i_prev;
-                    integral_n[(row, col)] = sum_n + n_prev;
-                } else {
-                    integral[(row, col)] = sum;
-                    integral_n[(row, col)] = sum_n;
-                }
-            }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
-                    old_progress = progress;
-                }
-            }
-        }
+        let conv_input = Arc::new(ConvolutionInput::new(
+            &input,
+            if is_rgb_image { Some(value2i) } else { None },
+        ));
+        let kernel = Arc::new(Kernel::separable(
+            vec![1f64; filter_size_y],
+            vec![1f64; filter_size_x],
+        ));
+        let filtered = convolve(
+            &conv_input,
+            &kernel,
+            NodataMode::Normalize,
+            nodata,
+            num_procs,
+            verbose,
+            reporter,
+        )?;
 
-        let i = Arc::new(integral); // wrap integral in an Arc
-        let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
         let mut output = Raster::initialize_using_file(&output_file, &input);
-        let (tx, rx) = mpsc::channel();
-        let mut num_procs = num_cpus::get() as isize;
-        let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let i = i.clone();
-            let i_n = i_n.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-                    Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-                } else {
-                    Box::new(|row: isize, col: isize| -> f64 {
-                        let value = input.get_value(row, col);
-                        if value != nodata {
-                            return value2i(value);
-                        }
-                        nodata
-                    })
-                };
-
-                let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
-                    // simply return the value.
-                    Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
-                } else {
-                    // convert it back into an rgb value, using the modified intensity value.
-                    Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
-                            let (h, s, _) = value2hsi(input.get_value(row, col));
-                            return hsi2value(h, s, value);
-                        }
-                        nodata
-                    })
-                };
-
-                let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
-                let mut n: i32;
-                let mut sum: f64;
-                let mut mean: f64;
-                let mut z: f64;
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    y1 = row - midpoint_y - 1;
-                    if y1 < 0 {
-                        y1 = 0;
-                    }
-
-                    y2 = row + midpoint_y;
-                    if y2 >= rows {
-                        y2 = rows - 1;
-                    }
-                    let mut data = vec![nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input_fn(row, col);
-                        if z != nodata {
-                            x1 = col - midpoint_x - 1;
-                            if x1 < 0 {
-                                x1 = 0;
-                            }
-
-                            x2 = col + midpoint_x;
-                            if x2 >= columns {
-                                x2 = columns - 1;
-                            }
-                            n = i_n[(y2, x2)] + i_n[(y1, x1)] - i_n[(y1, x2)] - i_n[(y2, x1)];
-                            if n > 0 {
-                                sum = i[(y2, x2)] + i[(y1, x1)] - i[(y1, x2)] - i[(y2, x1)];
-                                mean = sum / n as f64 + min_val;
-                                data[col as usize] = output_fn(row, col, mean);
-                            } else {
-                                data[col as usize] = output_fn(row, col, 0f64);
-                            }
-                        }
+        for (row, mut data) in filtered.into_iter().enumerate() {
+            if is_rgb_image {
+                // convert it back into an rgb value, using the modified intensity value.
+                for col in 0..columns {
+                    if !is_nodata(data[col as usize], nodata) {
+                        let (h, s, _) = value2hsi(input.get_value(row as isize, col));
+                        data[col as usize] = hsi2value(h, s, data[col as usize]);
                     }
-
-                    tx1.send((row, data)).unwrap();
-                }
-            });
-        }
-
-        for row in 0..rows {
-            let data = rx.recv().expect("Error receiving data from thread.");
-            output.set_row_data(data.0, data.1);
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
                 }
             }
+            output.set_row_data(row as isize, data);
         }
 
         let elapsed_time = get_formatted_elapsed_time(start);
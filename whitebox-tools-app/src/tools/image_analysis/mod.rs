@@ -2,8 +2,12 @@
 mod adaptive_filter;
 mod balance_contrast_enhancement;
 mod bilateral_filter;
+mod build_virtual_mosaic;
+mod burn_severity;
 mod change_vector_analysis;
 mod closing;
+mod colourize_raster;
+mod convolution;
 mod conservative_smoothing_filter;
 mod corner_detection;
 mod correct_vignetting;
@@ -13,6 +17,9 @@ mod diversity_filter;
 mod dog_filter;
 mod edge_preserving_mean_filter;
 mod emboss_filter;
+mod export_colour_composite;
+mod export_image_chips;
+mod export_web_tiles;
 mod fast_almost_gaussian_filter;
 mod flip_image;
 mod gamma_correction;
@@ -23,6 +30,7 @@ mod highpass_median_filter;
 mod histogram_equalization;
 mod histogram_matching;
 mod histogram_matching_two_images;
+mod hough_transform;
 mod ihs_to_rgb;
 mod image_stack_profile;
 mod integral_image;
@@ -34,34 +42,47 @@ mod line_detection_filter;
 mod line_thin;
 mod log_filter;
 mod majority_filter;
+mod mbtiles;
 mod max_filter;
 mod mean_filter;
 mod median_filter;
 mod min_filter;
 mod min_max_contrast_stretch;
 mod modified_k_means_clustering;
+mod morphological_filter;
+mod morphological_reconstruction;
 mod mosaic;
 mod mosaic_with_feathering;
 mod normalized_difference_index;
 mod olympic_filter;
+mod onnx_inference;
 mod opening;
 mod pan_sharpening;
 mod percentage_contrast_stretch;
 mod percentile_filter;
 mod prewitt_filter;
+mod quick_view;
 mod range_filter;
 mod remove_spurs;
+mod rendering;
 mod resample;
 mod rgb_to_ihs;
 mod roberts_filter;
+mod sar_decibel_conversion;
+mod sar_speckle_filter;
 mod scharr_filter;
 mod sigmoidal_contrast_stretch;
+mod skeletonize;
 mod sobel_filter;
+mod spectral_indices;
 mod split_colour_composite;
 mod stdev_contrast_stretch;
 mod stdev_filter;
+mod subpixel_boundary;
+mod surface_water_extraction;
 mod thicken_line;
 mod tophat;
+mod topographic_correction;
 mod total_filter;
 mod unsharp_masking;
 mod user_defined_weights_filter;
@@ -71,8 +92,11 @@ mod write_func_memory_insertion;
 pub use self::adaptive_filter::AdaptiveFilter;
 pub use self::balance_contrast_enhancement::BalanceContrastEnhancement;
 pub use self::bilateral_filter::BilateralFilter;
+pub use self::build_virtual_mosaic::BuildVirtualMosaic;
+pub use self::burn_severity::BurnSeverity;
 pub use self::change_vector_analysis::ChangeVectorAnalysis;
 pub use self::closing::Closing;
+pub use self::colourize_raster::ColourizeRaster;
 pub use self::conservative_smoothing_filter::ConservativeSmoothingFilter;
 pub use self::corner_detection::CornerDetection;
 pub use self::correct_vignetting::CorrectVignetting;
@@ -82,6 +106,9 @@ pub use self::diversity_filter::DiversityFilter;
 pub use self::dog_filter::DiffOfGaussianFilter;
 pub use self::edge_preserving_mean_filter::EdgePreservingMeanFilter;
 pub use self::emboss_filter::EmbossFilter;
+pub use self::export_colour_composite::ExportColourComposite;
+pub use self::export_image_chips::ExportImageChips;
+pub use self::export_web_tiles::ExportWebTiles;
 pub use self::fast_almost_gaussian_filter::FastAlmostGaussianFilter;
 pub use self::flip_image::FlipImage;
 pub use self::gamma_correction::GammaCorrection;
@@ -92,6 +119,7 @@ pub use self::highpass_median_filter::HighPassMedianFilter;
 pub use self::histogram_equalization::HistogramEqualization;
 pub use self::histogram_matching::HistogramMatching;
 pub use self::histogram_matching_two_images::HistogramMatchingTwoImages;
+pub use self::hough_transform::HoughTransform;
 pub use self::ihs_to_rgb::IhsToRgb;
 pub use self::image_stack_profile::ImageStackProfile;
 pub use self::integral_image::IntegralImage;
@@ -109,29 +137,41 @@ pub use self::median_filter::MedianFilter;
 pub use self::min_filter::MinimumFilter;
 pub use self::min_max_contrast_stretch::MinMaxContrastStretch;
 pub use self::modified_k_means_clustering::ModifiedKMeansClustering;
+pub use self::morphological_filter::MorphologicalFilter;
+pub use self::morphological_reconstruction::MorphologicalReconstruction;
 pub use self::mosaic::Mosaic;
 pub use self::mosaic_with_feathering::MosaicWithFeathering;
 pub use self::normalized_difference_index::NormalizedDifferenceIndex;
 pub use self::olympic_filter::OlympicFilter;
+pub use self::onnx_inference::OnnxInference;
 pub use self::opening::Opening;
 pub use self::pan_sharpening::PanchromaticSharpening;
 pub use self::percentage_contrast_stretch::PercentageContrastStretch;
 pub use self::percentile_filter::PercentileFilter;
 pub use self::prewitt_filter::PrewittFilter;
+pub use self::quick_view::QuickView;
 pub use self::range_filter::RangeFilter;
 pub use self::remove_spurs::RemoveSpurs;
 pub use self::resample::Resample;
 pub use self::rgb_to_ihs::RgbToIhs;
 pub use self::roberts_filter::RobertsCrossFilter;
+pub use self::sar_decibel_conversion::SarDecibelConversion;
+pub use self::sar_speckle_filter::SarSpeckleFilter;
 pub use self::scharr_filter::ScharrFilter;
 pub use self::sigmoidal_contrast_stretch::SigmoidalContrastStretch;
+pub use self::skeletonize::Skeletonize;
 pub use self::sobel_filter::SobelFilter;
+pub use self::spectral_indices::SpectralIndices;
 pub use self::split_colour_composite::SplitColourComposite;
 pub use self::stdev_contrast_stretch::StandardDeviationContrastStretch;
 pub use self::stdev_filter::StandardDeviationFilter;
+pub use self::subpixel_boundary::SubpixelBoundary;
+pub use self::surface_water_extraction::SurfaceWaterExtraction;
 pub use self::thicken_line::ThickenRasterLine;
 pub use self::tophat::TophatTransform;
+pub use self::topographic_correction::TopographicCorrection;
 pub use self::total_filter::TotalFilter;
 pub use self::unsharp_masking::UnsharpMasking;
 pub use self::user_defined_weights_filter::UserDefinedWeightsFilter;
 pub use self::write_func_memory_insertion::WriteFunctionMemoryInsertion;
+
@@ -10,7 +10,6 @@ use whitebox_raster::*;
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use crate::tools::*;
-use num_cpus;
 use rand::prelude::*;
 use std::env;
 use std::f64;
@@ -22,7 +21,7 @@ use std::path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This modified k-means algorithm is similar to that described by Mather and Koch (2011).
 /// The main difference between the traditional k-means and this technique is that the user
@@ -58,7 +57,7 @@ impl ModifiedKMeansClustering {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input Files".to_owned(),
-            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            flags: vec!["-i".to_owned(), "--input".to_owned(), "--inputs".to_owned()],
             description: "Input raster files.".to_owned(),
             parameter_type: ParameterType::FileList(ParameterFileType::Raster),
             default_value: None,
@@ -94,7 +93,7 @@ impl ModifiedKMeansClustering {
 
         parameters.push(ToolParameter {
             name: "Cluster Merger Distance".to_owned(),
-            flags: vec!["--merge_dist".to_owned()],
+            flags: vec!["--merger_dist".to_owned(), "--merge_dist".to_owned()],
             description: "Cluster merger distance".to_owned(),
             parameter_type: ParameterType::Float,
             default_value: None,
@@ -116,7 +115,7 @@ impl ModifiedKMeansClustering {
             description: "Minimum percent of cells changed between iterations before completion"
                 .to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("2.0".to_owned()),
+            default_value: Some("5.0".to_owned()),
             optional: true,
         });
 
@@ -196,121 +195,22 @@ impl WhiteboxTool for ModifiedKMeansClustering {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files_str = String::new();
-        let mut output_file = String::new();
-        let mut output_html_file = String::new();
-        let mut num_classes = 1000usize;
-        let mut max_iterations = 10usize;
-        let mut percent_changed_threshold = 5f64;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files_str = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut output_html_file = parsed.get_string("out_html").unwrap_or_default();
+        let mut num_classes = parsed.get_usize("start_clusters")?.unwrap_or(1000usize);
+        let max_iterations = parsed.get_usize("max_iterations")?.unwrap_or(10usize);
+        let percent_changed_threshold = parsed.get_f64("class_change").unwrap_or(5f64);
         // let mut initialization_mode = 1;
         // let mut min_class_size = 10;
         let mut merger_dist = 1f64;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" || flag_val == "-input" {
-                if keyval {
-                    input_files_str = vec[1].to_string();
-                } else {
-                    input_files_str = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-out_html" {
-                if keyval {
-                    output_html_file = vec[1].to_string();
-                } else {
-                    output_html_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-start_clusters" {
-                if keyval {
-                    num_classes = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    num_classes = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-merge_dist" || flag_val == "-merger_dist" {
-                if keyval {
-                    merger_dist = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    merger_dist = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-                merger_dist *= merger_dist;
-            } else if flag_val == "-max_iterations" {
-                if keyval {
-                    max_iterations = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    max_iterations = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-class_change" {
-                if keyval {
-                    percent_changed_threshold = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    percent_changed_threshold = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-                // } else if flag_val == "-initialize" {
-                //     if keyval {
-                //         if vec[1].to_string().to_lowercase().contains("rand") {
-                //             initialization_mode = 0;
-                //         }
-                //     } else {
-                //         if args[i + 1].to_string().to_lowercase().contains("diag") {
-                //             initialization_mode = 1;
-                //         }
-                //     }
-                // } else if flag_val == "-min_class_size" {
-                //     if keyval {
-                //         min_class_size = vec[1].to_string().parse::<f32>().expect(&format!("Error parsing {}", flag_val)) as usize;
-                //     } else {
-                //         min_class_size = args[i + 1].to_string().parse::<f32>().expect(&format!("Error parsing {}", flag_val)) as usize;
-                //     }
-            }
+        if let Some(value) = parsed.get_f64("merge_dist") {
+            merger_dist = value;
+            merger_dist *= merger_dist;
         }
 
         if verbose {
@@ -324,18 +224,12 @@ impl WhiteboxTool for ModifiedKMeansClustering {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
-        if !output_html_file.contains(&sep) {
-            output_html_file = format!("{}{}", working_directory, output_html_file);
-        }
+        output_html_file = resolve_path(&output_html_file, working_directory);
 
         if !output_html_file.ends_with(".html") && !output_html_file.is_empty() {
             output_html_file.push_str(".html");
@@ -388,9 +282,7 @@ impl WhiteboxTool for ModifiedKMeansClustering {
             println!("Reading file {} of {}", i + 1, num_files);
             if !input_files[i].trim().is_empty() {
                 let mut input_file = input_files[i].trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 input_raster.push(Raster::new(&input_file, "r")?);
                 nodata.push(input_raster[i].configs.nodata);
                 minimum.push(input_raster[i].configs.minimum);
@@ -413,8 +305,7 @@ impl WhiteboxTool for ModifiedKMeansClustering {
                     if input_raster[i].configs.rows as isize != rows
                         || input_raster[i].configs.columns as isize != columns
                     {
-                        return Err(Error::new(ErrorKind::InvalidInput,
-                            "All input images must share the same dimensions (rows and columns) and spatial extent."));
+                        return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
                     }
                 }
             }
@@ -474,12 +365,8 @@ impl WhiteboxTool for ModifiedKMeansClustering {
 
             let mut cells_changed = 0f64;
 
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let centres = Arc::new(class_centres.clone());
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
@@ -487,7 +374,7 @@ impl WhiteboxTool for ModifiedKMeansClustering {
                 let centres = centres.clone();
                 let nodata = nodata.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data = vec![-1isize; columns as usize];
                         let mut is_valid_data: bool;
@@ -499,7 +386,7 @@ impl WhiteboxTool for ModifiedKMeansClustering {
                             is_valid_data = true;
                             for i in 0..num_files {
                                 value[i] = input_raster[i].get_value(row, col);
-                                if value[i] == nodata[i] {
+                                if is_nodata(value[i], nodata[i]) {
                                     is_valid_data = false;
                                     break;
                                 }
@@ -526,7 +413,9 @@ impl WhiteboxTool for ModifiedKMeansClustering {
                                 }
                             }
                         }
-                        tx.send((row, data, class_centre_data)).unwrap();
+                        if tx.send((row, data, class_centre_data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -541,7 +430,7 @@ impl WhiteboxTool for ModifiedKMeansClustering {
                         which_class = data[col as usize] as usize;
                         z = output.get_value(row, col);
                         class = z as usize - 1usize;
-                        if z == out_nodata || which_class != class {
+                        if is_nodata(z, out_nodata) || which_class != class {
                             cells_changed += 1f64;
                             output.set_value(row, col, which_class as f64 + 1f64);
                         }
@@ -556,15 +445,11 @@ impl WhiteboxTool for ModifiedKMeansClustering {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Progress (loop {} of {}): {}%",
-                            loop_num + 1,
-                            max_iterations,
-                            progress
-                        );
+                        reporter.progress(&format!("Progress (loop {} of {})", loop_num + 1, max_iterations), progress);
                         old_progress = progress;
                     }
                 }
@@ -0,0 +1,396 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{
+    black_tophat, closing, dilate, erode, opening, white_tophat, StructuringElement,
+};
+use whitebox_common::structures::Array2D;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool applies a [mathematical morphology](https://en.wikipedia.org/wiki/Mathematical_morphology)
+/// operator to an input binary or greyscale raster (`--input`). The `--operation` may be:
+///
+/// | Operation | Description |
+/// | --------- | ----------- |
+/// | erosion | The minimum of the cells under the structuring element |
+/// | dilation | The maximum of the cells under the (reflected) structuring element |
+/// | opening | The dilation of the erosion, which removes bright features smaller than the element |
+/// | closing | The erosion of the dilation, which removes dark features smaller than the element |
+/// | white tophat | The difference between the image and its opening |
+/// | black tophat | The difference between the closing and the image |
+///
+/// Unlike the `Opening`, `Closing`, and `TophatTransform` tools, which use rectangular structuring
+/// elements, the structuring element can be a `square`, `disk`, `diamond`, or `cross` of a given odd
+/// `--size` in cells, or a `custom` element read from a text file (`--element`). The file contains one
+/// line for each row of the element, with the values of a row separated by commas or spaces; cells with
+/// non-zero values are members of the element. The element must have odd dimensions and its origin is
+/// its centre cell. Asymmetric elements are reflected through the origin for dilation, such that opening
+/// and closing remain idempotent.
+///
+/// When the `--binary` flag is specified, the input is treated as a mask in which every non-zero cell is
+/// foreground, and the output contains ones and zeros. Otherwise, the operators are applied to the
+/// greyscale values, which is how they are also used to remove artifacts from DEMs, e.g. opening a
+/// DEM with a disk slightly larger than the buildings it contains removes them.
+///
+/// NoData cells are ignored in the neighbourhood of each cell and remain NoData in the output.
+///
+/// # See Also
+/// `MorphologicalReconstruction`, `Opening`, `Closing`, `TophatTransform`, `MinimumFilter`, `MaximumFilter`
+pub struct MorphologicalFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl MorphologicalFilter {
+    pub fn new() -> MorphologicalFilter {
+        // public constructor
+        let name = "MorphologicalFilter".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Applies a morphological operator with an arbitrary structuring element to a binary or greyscale image.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Operation".to_owned(),
+            flags: vec!["--operation".to_owned()],
+            description: "Morphological operation; one of 'erosion', 'dilation', 'opening', 'closing', 'white tophat', and 'black tophat'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "erosion".to_owned(),
+                "dilation".to_owned(),
+                "opening".to_owned(),
+                "closing".to_owned(),
+                "white tophat".to_owned(),
+                "black tophat".to_owned(),
+            ]),
+            default_value: Some("opening".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Structuring Element Shape".to_owned(),
+            flags: vec!["--shape".to_owned()],
+            description: "Shape of the structuring element; one of 'square', 'disk', 'diamond', 'cross', and 'custom'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "square".to_owned(),
+                "disk".to_owned(),
+                "diamond".to_owned(),
+                "cross".to_owned(),
+                "custom".to_owned(),
+            ]),
+            default_value: Some("square".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Structuring Element Size".to_owned(),
+            flags: vec!["--size".to_owned()],
+            description: "Width of the structuring element, in cells (odd).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Custom Structuring Element File (optional)".to_owned(),
+            flags: vec!["--element".to_owned()],
+            description: "Text file of the custom structuring element, with non-zero values for its members.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Text),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Treat input as a binary mask?".to_owned(),
+            flags: vec!["--binary".to_owned()],
+            description: "Optional flag to treat the input as a binary mask of non-zero cells.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif -o=output.tif --operation=opening --shape=disk --size=9
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=mask.tif -o=output.tif --operation=closing --shape=custom --element=element.txt --binary",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        MorphologicalFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for MorphologicalFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut operation = String::from("opening");
+        let mut shape = String::from("square");
+        let mut size = parsed.get_usize("size")?.unwrap_or(3usize);
+        let mut element_file = parsed.get_string("element").unwrap_or_default();
+        let binary = parsed.get_bool("binary");
+
+        if let Some(value) = parsed.get_string("operation") {
+            operation = value.to_lowercase();
+        }
+        if let Some(value) = parsed.get_string("shape") {
+            shape = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if size < 1 {
+            size = 1;
+        }
+        if size % 2 == 0 {
+            size += 1;
+        }
+
+        let element = if shape.contains("custom") {
+            if element_file.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "A custom structuring element requires an --element file.",
+                ));
+            }
+            element_file = resolve_input_path(&element_file, working_directory)?;
+            let f = BufReader::new(File::open(element_file.clone())?);
+            let mut matrix = vec![];
+            for line in f.lines() {
+                reporter.check_cancelled()?;
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let mut row = vec![];
+                for v in line.split(|c| c == ',' || c == ' ').filter(|v| !v.trim().is_empty()) {
+                    let v = v.trim().parse::<f64>().map_err(|_| {
+                        Error::new(
+                            ErrorKind::InvalidData,
+                            format!("Unable to parse structuring element value '{}'.", v),
+                        )
+                    })?;
+                    row.push(v != 0f64);
+                }
+                matrix.push(row);
+            }
+            match StructuringElement::from_matrix(&matrix) {
+                Some(e) => e,
+                None => {
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The structuring element file must contain an odd number of rows and columns, with the same number of values in each row, and at least one non-zero value."))
+                }
+            }
+        } else if shape.contains("disk") || shape.contains("circ") {
+            StructuringElement::disk(size)
+        } else if shape.contains("diamond") {
+            StructuringElement::diamond(size)
+        } else if shape.contains("cross") {
+            StructuringElement::cross(size)
+        } else if shape.contains("square") {
+            StructuringElement::rectangle(size, size)
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unrecognized --shape; it must be one of 'square', 'disk', 'diamond', 'cross', or 'custom'.",
+            ));
+        };
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let mut image: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            let mut data = input.get_row_data(row);
+            if binary {
+                for z in data.iter_mut() {
+                    if !is_nodata(*z, nodata) {
+                        *z = if *z != 0f64 { 1f64 } else { 0f64 };
+                    }
+                }
+            }
+            image.set_row_data(row, data);
+        }
+
+        if verbose {
+            println!("Performing {}...", operation);
+        }
+        let result = if operation.contains("white") {
+            white_tophat(&image, &element, &|| reporter.check_cancelled())?
+        } else if operation.contains("black") {
+            black_tophat(&image, &element, &|| reporter.check_cancelled())?
+        } else if operation.contains("ero") {
+            erode(&image, &element, &|| reporter.check_cancelled())?
+        } else if operation.contains("dil") {
+            dilate(&image, &element, &|| reporter.check_cancelled())?
+        } else if operation.contains("open") {
+            opening(&image, &element, &|| reporter.check_cancelled())?
+        } else if operation.contains("clos") {
+            closing(&image, &element, &|| reporter.check_cancelled())?
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Unrecognized --operation; it must be one of 'erosion', 'dilation', 'opening', 'closing', 'white tophat', or 'black tophat'.",
+            ));
+        };
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        if binary {
+            output.configs.data_type = DataType::U8;
+            output.configs.nodata = 255f64;
+            output.configs.palette = "qual.plt".to_string();
+            output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        }
+        let out_nodata = output.configs.nodata;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            let mut data = result.get_row_data(row);
+            for z in data.iter_mut() {
+                if is_nodata(*z, nodata) {
+                    *z = out_nodata;
+                }
+            }
+            output.set_row_data(row, data);
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Operation: {}", operation));
+        if element_file.is_empty() {
+            output.add_metadata_entry(format!("Structuring element: {} of size {}", shape, size));
+        } else {
+            output.add_metadata_entry(format!("Structuring element file: {}", element_file));
+        }
+        output.add_metadata_entry(format!("Binary: {}", binary));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{reconstruct_by_dilation, reconstruct_by_erosion};
+use whitebox_common::structures::Array2D;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool performs a [morphological reconstruction](https://doi.org/10.1109/83.217222) of a
+/// marker image (`--marker`) constrained by a mask image (`--mask`). Reconstruction by dilation
+/// (`--operation=dilation`, the default) repeatedly dilates the marker, using 8-connectivity, while
+/// limiting it to be no higher than the mask, until it no longer changes. Every regional maximum of
+/// the mask that does not contain a marked cell is flattened. Reconstruction by erosion is its dual:
+/// the marker is repeatedly eroded while being kept no lower than the mask, which fills the regional
+/// minima of the mask that are not marked. Marker values on the wrong side of the mask are first
+/// set to the mask value.
+///
+/// Reconstruction is the basis of many image analysis and DEM processing operations, such as:
+///
+/// - removing objects that touch the image edges from a binary mask, by reconstructing from a marker of
+///   the edge cells and subtracting the result from the mask;
+/// - removing peaks lower than *h* from a DEM, by reconstructing the DEM by dilation from the DEM
+///   lowered by *h* (the h-maxima transform); and
+/// - filling the depressions in a DEM, by reconstructing the DEM by erosion from a marker equal to the
+///   DEM along its edges and to its maximum elsewhere.
+///
+/// Unlike openings and closings, which also alter the shapes of the features that they retain,
+/// reconstruction preserves the exact shapes of the retained features. The two rasters must have the
+/// same dimensions. NoData cells in the mask act as barriers and remain NoData in the output; NoData
+/// cells in the marker are treated as unmarked.
+///
+/// # Reference
+/// Vincent, L. (1993). Morphological grayscale reconstruction in image analysis: applications and
+/// efficient algorithms. *IEEE Transactions on Image Processing*, 2(2), 176-201.
+///
+/// # See Also
+/// `MorphologicalFilter`, `FillDepressions`, `Opening`, `Closing`
+pub struct MorphologicalReconstruction {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl MorphologicalReconstruction {
+    pub fn new() -> MorphologicalReconstruction {
+        // public constructor
+        let name = "MorphologicalReconstruction".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Performs a morphological reconstruction by dilation or erosion of a marker image under a mask image.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Marker File".to_owned(),
+            flags: vec!["--marker".to_owned()],
+            description: "Input marker raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Mask File".to_owned(),
+            flags: vec!["--mask".to_owned()],
+            description: "Input mask raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Operation".to_owned(),
+            flags: vec!["--operation".to_owned()],
+            description: "Reconstruction by 'dilation' or by 'erosion'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "dilation".to_owned(),
+                "erosion".to_owned(),
+            ]),
+            default_value: Some("dilation".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --marker=dem_lowered.tif --mask=dem.tif -o=output.tif --operation=dilation",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        MorphologicalReconstruction {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for MorphologicalReconstruction {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut marker_file = parsed.get_string("marker").unwrap_or_default();
+        let mut mask_file = parsed.get_string("mask").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut operation = String::from("dilation");
+
+        if let Some(value) = parsed.get_string("operation") {
+            operation = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let by_dilation = if operation.contains("dil") {
+            true
+        } else if operation.contains("ero") {
+            false
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --operation parameter must be either 'dilation' or 'erosion'.",
+            ));
+        };
+
+        marker_file = resolve_input_path(&marker_file, working_directory)?;
+        mask_file = resolve_input_path(&mask_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let marker = Raster::new(&marker_file, "r")?;
+        let mask = Raster::new(&mask_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = mask.configs.rows as isize;
+        let columns = mask.configs.columns as isize;
+        if marker.configs.rows as isize != rows || marker.configs.columns as isize != columns {
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The marker and mask rasters must have the same number of rows and columns."));
+        }
+        let nodata = mask.configs.nodata;
+        let mut marker_data: Array2D<f64> =
+            Array2D::new(rows, columns, marker.configs.nodata, marker.configs.nodata)?;
+        let mut mask_data: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for row in 0..rows {
+            marker_data.set_row_data(row, marker.get_row_data(row));
+            mask_data.set_row_data(row, mask.get_row_data(row));
+        }
+
+        if verbose {
+            println!("Reconstructing...");
+        }
+        let result = if by_dilation {
+            reconstruct_by_dilation(&marker_data, &mask_data, &|| reporter.check_cancelled())?
+        } else {
+            reconstruct_by_erosion(&marker_data, &mask_data, &|| reporter.check_cancelled())?
+        };
+
+        let mut output = Raster::initialize_using_file(&output_file, &mask);
+        for row in 0..rows {
+            output.set_row_data(row, result.get_row_data(row));
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Marker file: {}", marker_file));
+        output.add_metadata_entry(format!("Mask file: {}", mask_file));
+        output.add_metadata_entry(format!(
+            "Operation: reconstruction by {}",
+            if by_dilation { "dilation" } else { "erosion" }
+        ));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
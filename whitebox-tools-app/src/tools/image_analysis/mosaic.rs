@@ -9,7 +9,6 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::RectangleWithData;
 use crate::tools::*;
-use num_cpus;
 use rstar::RTree;
 use std::env;
 use std::f64;
@@ -18,7 +17,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool will create an image mosaic from one or more input image files using
 /// one of three resampling methods including, nearest neighbour, bilinear interpolation,
@@ -151,57 +150,26 @@ impl WhiteboxTool for Mosaic {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut method = String::from("nn");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-method" {
-                method = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                if method.to_lowercase().contains("nn") || method.to_lowercase().contains("nearest")
-                {
-                    method = "nn".to_string();
-                } else if method.to_lowercase().contains("bilinear")
-                    || method.to_lowercase().contains("bi")
-                {
-                    method = "bilinear".to_string();
-                } else if method.to_lowercase().contains("cc")
-                    || method.to_lowercase().contains("cubic")
-                {
-                    method = "cc".to_string();
-                }
+        if let Some(value) = parsed.get_string("method") {
+            method = value;
+            if method.to_lowercase().contains("nn") || method.to_lowercase().contains("nearest")
+            {
+                method = "nn".to_string();
+            } else if method.to_lowercase().contains("bilinear")
+                || method.to_lowercase().contains("bi")
+            {
+                method = "bilinear".to_string();
+            } else if method.to_lowercase().contains("cc")
+                || method.to_lowercase().contains("cubic")
+            {
+                method = "cc".to_string();
             }
         }
 
@@ -216,8 +184,6 @@ impl WhiteboxTool for Mosaic {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -269,9 +235,7 @@ impl WhiteboxTool for Mosaic {
             }
         }
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         /*
         let mut cmd = input_files.split(";");
@@ -313,9 +277,7 @@ impl WhiteboxTool for Mosaic {
             let value = &(input_vec[a]);
             if !value.trim().is_empty() {
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 let res = Raster::new(&input_file, "r");
                 if res.is_ok() {
                     inputs.push(res.unwrap()); //Raster::new(&input_file, "r")?).expect(&format!("Error reading file: {}", value)));
@@ -394,10 +356,11 @@ impl WhiteboxTool for Mosaic {
                     "There is a problem with the list of input files. At least one specified input is empty."));
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * a as f64 / (num_files - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -454,12 +417,8 @@ impl WhiteboxTool for Mosaic {
         let y = Arc::new(y);
         let inputs = Arc::new(inputs);
         let nodata_vals = Arc::new(nodata_vals);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         if method == "nn" {
             for tid in 0..num_procs {
@@ -469,7 +428,7 @@ impl WhiteboxTool for Mosaic {
                 let y = y.clone();
                 let tx = tx.clone();
                 let tree = tree.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z: f64;
                     let (mut col_src, mut row_src): (isize, isize);
                     let mut i: usize;
@@ -487,13 +446,15 @@ impl WhiteboxTool for Mosaic {
                                 col_src = inputs[i].get_column_from_x(x[col as usize]);
                                 z = inputs[i].get_value(row_src, col_src);
 
-                                if z != nodata_vals[i] {
+                                if !is_nodata(z, nodata_vals[i]) {
                                     data[col as usize] = z;
                                     break;
                                 }
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -501,14 +462,15 @@ impl WhiteboxTool for Mosaic {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
-                    if data[col as usize] != nodata {
+                    if !is_nodata(data[col as usize], nodata) {
                         output.set_value(row, col, data[col as usize]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -524,7 +486,7 @@ impl WhiteboxTool for Mosaic {
                 let y = y.clone();
                 let tx = tx.clone();
                 let tree = tree.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z: f64;
                     let shift_x = [-1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2];
                     let shift_y = [-1, -1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2];
@@ -569,10 +531,10 @@ impl WhiteboxTool for Mosaic {
                                     dy = row_n as f64 - row_src;
                                     dx = col_n as f64 - col_src;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
+                                    if (dx + dy) != 0f64 && !is_nodata(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
+                                    } else if is_nodata(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         data[col as usize] = neighbour[n][0];
@@ -590,21 +552,24 @@ impl WhiteboxTool for Mosaic {
                                 }
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns as usize {
-                    if data[col] != nodata {
+                    if !is_nodata(data[col], nodata) {
                         output.set_value(row, col as isize, data[col]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -620,7 +585,7 @@ impl WhiteboxTool for Mosaic {
                 let y = y.clone();
                 let tx = tx.clone();
                 let tree = tree.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z: f64;
                     let shift_x = [0, 1, 0, 1];
                     let shift_y = [0, 0, 1, 1];
@@ -660,10 +625,10 @@ impl WhiteboxTool for Mosaic {
                                     dy = row_n as f64 - row_src;
                                     dx = col_n as f64 - col_src;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
+                                    if (dx + dy) != 0f64 && !is_nodata(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
+                                    } else if is_nodata(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         data[col as usize] = neighbour[n][0];
@@ -681,21 +646,24 @@ impl WhiteboxTool for Mosaic {
                                 }
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns as usize {
-                    if data[col] != nodata {
+                    if !is_nodata(data[col], nodata) {
                         output.set_value(row, col as isize, data[col]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool will create a mosaic from two input images. It is similar in operation to the `Mosaic` tool,
 /// however, this tool is the preferred method of mosaicing images when there is significant overlap between
@@ -168,82 +167,28 @@ impl WhiteboxTool for MosaicWithFeathering {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1 = String::new();
-        let mut input_file2 = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut method = String::from("cc");
-        let mut distance_weight = 4.0;
+        let distance_weight = parsed.get_f64("weight").unwrap_or(4.0);
         // let mut histo_match = false;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i1" || flag_val == "-input1" {
-                input_file1 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-i2" || flag_val == "-input2" {
-                input_file2 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-method" {
-                method = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                if method.to_lowercase().contains("nn") || method.to_lowercase().contains("nearest")
-                {
-                    method = "nn".to_string();
-                } else if method.to_lowercase().contains("bilinear")
-                    || method.to_lowercase().contains("bi")
-                {
-                    method = "bilinear".to_string();
-                } else if method.to_lowercase().contains("cc")
-                    || method.to_lowercase().contains("cubic")
-                {
-                    method = "cc".to_string();
-                }
-            // } else if flag_val == "-histo_match" {
-            // if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-            //     histo_match = true;
-            //    }
-            } else if flag_val == "-weight" {
-                distance_weight = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
+        if let Some(value) = parsed.get_string("method") {
+            method = value;
+            if method.to_lowercase().contains("nn") || method.to_lowercase().contains("nearest") {
+                method = "nn".to_string();
+            } else if method.to_lowercase().contains("bilinear")
+                || method.to_lowercase().contains("bi")
+            {
+                method = "bilinear".to_string();
+            } else if method.to_lowercase().contains("cc")
+                || method.to_lowercase().contains("cubic")
+            {
+                method = "cc".to_string();
             }
         }
 
@@ -258,20 +203,12 @@ impl WhiteboxTool for MosaicWithFeathering {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let start = Instant::now();
 
@@ -337,30 +274,27 @@ impl WhiteboxTool for MosaicWithFeathering {
         configs.data_type = input1.configs.data_type;
         configs.photometric_interp = input1.configs.photometric_interp;
         configs.palette = input1.configs.palette.clone();
-        configs.projection = input1.configs.projection.clone();
-        configs.epsg_code = input1.configs.epsg_code.clone();
+        configs.set_crs(&input1.configs.crs());
 
         let mut output = Raster::initialize_using_config(&output_file, &configs);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
 
         // create the minimum edge distance rasters
         for tid in 0..num_procs {
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 for row in (0..rows1).filter(|r| r % num_procs == tid) {
                     let mut data = vec![0u32; columns1 as usize];
                     for col in 0..columns1 {
                         data[col as usize] =
                             col.min(row.min((columns1 - col - 1).min(rows1 - row - 1))) as u32;
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -369,10 +303,11 @@ impl WhiteboxTool for MosaicWithFeathering {
         for row in 0..rows1 {
             let data = rx.recv().expect("Error receiving data from thread.");
             dist1_raster.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating distances (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Calculating distances (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -380,14 +315,16 @@ impl WhiteboxTool for MosaicWithFeathering {
 
         for tid in 0..num_procs {
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 for row in (0..rows2).filter(|r| r % num_procs == tid) {
                     let mut data = vec![0u32; columns2 as usize];
                     for col in 0..columns2 {
                         data[col as usize] =
                             col.min(row.min((columns2 - col - 1).min(rows2 - row - 1))) as u32;
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -396,10 +333,11 @@ impl WhiteboxTool for MosaicWithFeathering {
         for row in 0..rows2 {
             let data = rx.recv().expect("Error receiving data from thread.");
             dist2_raster.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating distances (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Calculating distances (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -430,7 +368,7 @@ impl WhiteboxTool for MosaicWithFeathering {
                 let dist1_raster = dist1_raster.clone();
                 let dist2_raster = dist2_raster.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut col_src1, mut row_src1): (isize, isize);
                     let (mut col_src2, mut row_src2): (isize, isize);
                     let (mut z1, mut z2): (f64, f64);
@@ -451,7 +389,7 @@ impl WhiteboxTool for MosaicWithFeathering {
                             row_src2 = input2.get_row_from_y(y[row as usize]);
                             col_src2 = input2.get_column_from_x(x[col as usize]);
                             z2 = input2.get_value(row_src2, col_src2);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                                 dist1 = dist1_raster.get_value(row_src1, col_src1) as f64;
                                 dist2 = dist2_raster.get_value(row_src2, col_src2) as f64;
                                 sum_dist =
@@ -478,13 +416,15 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     data[col as usize] =
                                         ((255u32 << 24) | (blue << 16) | (green << 8) | red) as f64;
                                 }
-                            } else if z1 != nodata1 {
+                            } else if !is_nodata(z1, nodata1) {
                                 data[col as usize] = z1;
-                            } else if z2 != nodata2 {
+                            } else if !is_nodata(z2, nodata2) {
                                 data[col as usize] = z2;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -492,10 +432,11 @@ impl WhiteboxTool for MosaicWithFeathering {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(row, data);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -513,7 +454,7 @@ impl WhiteboxTool for MosaicWithFeathering {
                 let dist1_raster = dist1_raster.clone();
                 let dist2_raster = dist2_raster.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let shift_x = if method == "cc" {
                         vec![-1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2]
                     } else {
@@ -576,10 +517,10 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     dy = row_n as f64 - row_src1;
                                     dx = col_n as f64 - col_src1;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata1 {
+                                    if (dx + dy) != 0f64 && !is_nodata(neighbour[n][0], nodata1) {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata1 {
+                                    } else if is_nodata(neighbour[n][0], nodata1) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         neighbour[n][1] = large_value;
@@ -601,10 +542,10 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     dy = row_n as f64 - row_src2;
                                     dx = col_n as f64 - col_src2;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata2 {
+                                    if (dx + dy) != 0f64 && !is_nodata(neighbour[n][0], nodata2) {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata2 {
+                                    } else if is_nodata(neighbour[n][0], nodata2) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         neighbour[n][1] = large_value;
@@ -643,11 +584,11 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     dy = row_n as f64 - row_src1;
                                     dx = col_n as f64 - col_src1;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata1 {
+                                    if (dx + dy) != 0f64 && !is_nodata(neighbour[n][0], nodata1) {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
                                         image1_valid = true;
-                                    } else if neighbour[n][0] == nodata1 {
+                                    } else if is_nodata(neighbour[n][0], nodata1) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         neighbour[n][1] = large_value;
@@ -678,11 +619,11 @@ impl WhiteboxTool for MosaicWithFeathering {
                                     dy = row_n as f64 - row_src2;
                                     dx = col_n as f64 - col_src2;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata2 {
+                                    if (dx + dy) != 0f64 && !is_nodata(neighbour[n][0], nodata2) {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
                                         image2_valid = true;
-                                    } else if neighbour[n][0] == nodata2 {
+                                    } else if is_nodata(neighbour[n][0], nodata2) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         neighbour[n][1] = large_value;
@@ -733,17 +674,20 @@ impl WhiteboxTool for MosaicWithFeathering {
                                 }
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(row, data);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to calculate a normalized difference index (NDI) from two bands of multispectral image data.
 /// A NDI of two band images (`image1` and `image2`) takes the general form:
@@ -196,83 +195,22 @@ impl WhiteboxTool for NormalizedDifferenceIndex {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input1_file = String::new();
-        let mut input2_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input1_file = parsed.get_string("input1").unwrap_or_default();
+        let mut input2_file = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut clip_amount = 0.0;
         // let mut osavi_mode = false;
-        let mut correction_factor = 0.0;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-input1" {
-                input1_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-input2" {
-                input2_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-clip" {
-                clip_amount = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if clip_amount < 0.0 {
-                    clip_amount = 0.0;
-                } else if clip_amount > 30.0 {
-                    clip_amount = 30.0;
-                }
-            } else if flag_val == "-correction" {
-                correction_factor = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
+        let correction_factor = parsed.get_f64("correction").unwrap_or(0.0);
+        if let Some(value) = parsed.get_f64("clip") {
+            clip_amount = value;
+            if clip_amount < 0.0 {
+                clip_amount = 0.0;
+            } else if clip_amount > 30.0 {
+                clip_amount = 30.0;
             }
-            // } else if flag_val == "-osavi" {
-            // if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-            //     osavi_mode = true;
-            //     correction_factor = 0.16;
-            // }
-            // }
         }
 
         if verbose {
@@ -286,20 +224,12 @@ impl WhiteboxTool for NormalizedDifferenceIndex {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input1_file.contains(&sep) && !input1_file.contains("/") {
-            input1_file = format!("{}{}", working_directory, input1_file);
-        }
-        if !input2_file.contains(&sep) && !input2_file.contains("/") {
-            input2_file = format!("{}{}", working_directory, input2_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input1_file = resolve_input_path(&input1_file, working_directory)?;
+        input2_file = resolve_input_path(&input2_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -315,35 +245,29 @@ impl WhiteboxTool for NormalizedDifferenceIndex {
 
         // make sure the input files have the same size
         if nir.configs.rows != red.configs.rows || nir.configs.columns != red.configs.columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&nir, &red, verbose);
 
         let start = Instant::now();
 
         let mut output = Raster::initialize_using_file(&output_file, &nir);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let nir = nir.clone();
             let red = red.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut z_nir, mut z_red): (f64, f64);
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nir_nodata; columns as usize];
                     for col in 0..columns {
                         z_nir = nir[(row, col)];
                         z_red = red[(row, col)];
-                        if z_nir != nir_nodata && z_red != red_nodata {
+                        if !is_nodata(z_nir, nir_nodata) && !is_nodata(z_red, red_nodata) {
                             if z_nir + z_red != 0.0 || correction_factor > 0f64 {
                                 data[col as usize] =
                                     (z_nir - z_red) / (z_nir + z_red + correction_factor);
@@ -352,7 +276,9 @@ impl WhiteboxTool for NormalizedDifferenceIndex {
                             }
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -360,10 +286,11 @@ impl WhiteboxTool for NormalizedDifferenceIndex {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
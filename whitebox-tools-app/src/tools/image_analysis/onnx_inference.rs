@@ -0,0 +1,718 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: The ONNX runtime (tract) is an optional dependency, enabled with the 'onnx' feature, because
+it considerably increases the build time and size of the program. Without the feature, the tool is
+still listed, so that its help and parameters are available, but running it returns an error.
+*/
+
+use self::onnx_model::OnnxModel;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::collections::VecDeque;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool applies a machine learning model in the ONNX format, e.g. one trained with PyTorch,
+/// TensorFlow or scikit-learn and exported to ONNX, to a multiband raster stack, so that models trained
+/// outside of WhiteboxTools can be applied to large images. The image bands (`--inputs`) are provided
+/// as a list of single-band rasters, in the order of the model's input channels.
+///
+/// The model is applied in one of two modes (`--mode`):
+///
+/// - `pixel`: the model is applied to the vector of band values of each grid cell independently, as is
+///   typical of random forest, gradient boosting and multi-layer perceptron models. The model's input
+///   has the shape (batch, bands) and its output the shape (batch, outputs) or (batch). Cells are passed
+///   to the model in batches of `--batch_size`.
+/// - `patch`: the model is applied to square patches of `--patch_size` grid cells, as is typical of
+///   convolutional networks for semantic segmentation (e.g. U-Net). The model's input has the shape
+///   (1, bands, rows, columns), or (1, rows, columns, bands) if `--channels_last` is specified, and its
+///   output has the same layout, with the same number of rows and columns as the input, or the shape
+///   (1, rows, columns). Neighbouring patches overlap by `--overlap` cells, and the outputs of
+///   overlapping patches are blended with weights that taper linearly over the overlap, which avoids
+///   the seams that are otherwise found at the patch edges. The patches at the right and bottom edges
+///   of the raster are shifted inwards so that they do not extend beyond it.
+///
+/// The input values may be normalized before they are passed to the model, as `(z - mean) / std`, with
+/// the per-band means (`--mean`) and standard deviations (`--std`) used to train the model, given as a
+/// list of one value per band, or a single value used for all bands. NoData cells are input as zero,
+/// after normalization, and a cell that is NoData in any band is NoData in the output.
+///
+/// The output (`--output_type`) is either a `class` raster, which contains the index of the model
+/// output (channel) with the highest score, or the value of a single-output model rounded to the
+/// nearest integer (e.g. the class labels output by scikit-learn classifiers), or a `value` raster,
+/// which contains the value of the model output `--channel` (numbered from 0), e.g. for regression
+/// models or class probabilities. Models with integer outputs are treated as outputting class labels,
+/// which are not blended, and each cell takes the label of the patch whose centre is nearest.
+///
+/// The model's first input and first output are used. ONNX support is an optional feature of
+/// WhiteboxTools, and this tool returns an error if it is run by a build of WhiteboxTools that was
+/// compiled without it (i.e. without `cargo build --features onnx`).
+///
+/// # See Also
+/// `ExportImageChips`, `KMeansClustering`
+pub struct OnnxInference {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl OnnxInference {
+    pub fn new() -> OnnxInference {
+        // public constructor
+        let name = "OnnxInference".to_string();
+        let toolbox = "Machine Learning".to_string();
+        let description =
+            "Applies an ONNX machine learning model to a multiband raster stack."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Image Bands".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input image band raster files, in the order of the model's input channels.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input ONNX Model File".to_owned(),
+            flags: vec!["--model".to_owned()],
+            description: "Input ONNX model file (*.onnx).".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Any),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Inference Mode".to_owned(),
+            flags: vec!["--mode".to_owned()],
+            description: "Inference mode; 'pixel' applies the model to each cell, 'patch' to square patches.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["pixel".to_owned(), "patch".to_owned()]),
+            default_value: Some("patch".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Patch Size (cells)".to_owned(),
+            flags: vec!["--patch_size".to_owned()],
+            description: "Width and height of the model's input patches, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("256".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Patch Overlap (cells)".to_owned(),
+            flags: vec!["--overlap".to_owned()],
+            description: "Overlap of neighbouring patches, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("32".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Batch Size (cells)".to_owned(),
+            flags: vec!["--batch_size".to_owned()],
+            description: "Number of cells passed to the model at a time in pixel mode.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1024".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Type".to_owned(),
+            flags: vec!["--output_type".to_owned()],
+            description: "Output type; 'class' outputs the class with the highest score, 'value' a model output.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["class".to_owned(), "value".to_owned()]),
+            default_value: Some("class".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Channel".to_owned(),
+            flags: vec!["--channel".to_owned()],
+            description: "Model output (channel) written to a value output, numbered from 0.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Means (optional)".to_owned(),
+            flags: vec!["--mean".to_owned()],
+            description: "Normalization means, one per band or a single value, e.g. '0.48,0.46,0.41'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Standard Deviations (optional)".to_owned(),
+            flags: vec!["--std".to_owned()],
+            description: "Normalization standard deviations, one per band or a single value.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Channels Last?".to_owned(),
+            flags: vec!["--channels_last".to_owned()],
+            description: "Are the model's patches in (1, rows, columns, bands) order?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Alignment".to_owned(),
+            flags: vec!["--align".to_owned()],
+            description: "Input alignment mode; 'auto' resamples the other inputs to the grid of the first band.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["none".to_owned(), "auto".to_owned()]),
+            default_value: Some("none".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='red.tif;green.tif;blue.tif;nir.tif' --model=unet.onnx -o=landcover.tif --mode=patch --patch_size=256 --overlap=32 --mean=0.1,0.1,0.1,0.2 --std=0.05
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='red.tif;green.tif;blue.tif;nir.tif' --model=biomass.onnx -o=biomass.tif --mode=pixel --output_type=value", short_exe, name).replace("*", &sep);
+
+        OnnxInference {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for OnnxInference {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_list("inputs");
+        let mut model_file = parsed.get_string("model").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mode = parsed.get_string("mode").unwrap_or_default();
+        let patch_size = parsed.get_usize("patch_size")?.unwrap_or(256);
+        let overlap = parsed.get_usize("overlap")?.unwrap_or(32);
+        let batch_size = parsed.get_usize("batch_size")?.unwrap_or(1024).max(1);
+        let output_type = parsed.get_string("output_type").unwrap_or_default();
+        let channel = parsed.get_usize("channel")?.unwrap_or(0);
+        let means_str = parsed.get_string("mean").unwrap_or_default();
+        let stds_str = parsed.get_string("std").unwrap_or_default();
+        let channels_last = parsed.get_bool("channels_last");
+        let align = parsed.get_string("align").unwrap_or_default() == "auto";
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let class_output = output_type == "class";
+        if mode == "patch" && (patch_size < 2 || overlap >= patch_size / 2) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The patch size must be at least 2 grid cells and the overlap less than half of the patch size.",
+            ));
+        }
+        if input_files.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one input image band (--inputs) must be specified.",
+            ));
+        }
+        let num_bands = input_files.len();
+        let means = parse_band_values(&means_str, num_bands, 0f64, "--mean")?;
+        let stds = parse_band_values(&stds_str, num_bands, 1f64, "--std")?;
+        if stds.iter().any(|&s| s == 0f64) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The normalization standard deviations (--std) must not be zero.",
+            ));
+        }
+
+        model_file = resolve_input_path(&model_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let mut bands: Vec<Raster> = Vec::with_capacity(num_bands);
+        for f in &input_files {
+            let file_name = resolve_input_path(f, working_directory)?;
+            let band = if bands.is_empty() {
+                Raster::new(&file_name, "r")?
+            } else {
+                read_secondary_raster(&file_name, &bands[0], align, true, verbose)?
+            };
+            bands.push(band);
+        }
+        let rows = bands[0].configs.rows;
+        let columns = bands[0].configs.columns;
+
+        let start = Instant::now();
+
+        // The normalized band values of a cell, or None if the cell is NoData in any band.
+        let cell_values = |row: usize, col: usize| -> Option<Vec<f32>> {
+            let mut values = Vec::with_capacity(num_bands);
+            for (b, band) in bands.iter().enumerate() {
+                let z = band.get_value(row as isize, col as isize);
+                if is_nodata(z, band.configs.nodata) {
+                    return None;
+                }
+                values.push(((z - means[b]) / stds[b]) as f32);
+            }
+            Some(values)
+        };
+
+        let out_nodata = -32768f64;
+        let mut output = Raster::initialize_using_file(&output_file, &bands[0]);
+        output.configs.nodata = out_nodata;
+        output.reinitialize_values(out_nodata);
+        if class_output {
+            output.configs.data_type = DataType::I16;
+            output.configs.palette = "qual.plt".to_string();
+            output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        } else {
+            output.configs.data_type = DataType::F32;
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        if mode == "pixel" {
+            let model = OnnxModel::load(&model_file, &[batch_size, num_bands])?;
+            let mut batch_data = Vec::with_capacity(batch_size * num_bands);
+            let mut batch_cells: Vec<(usize, usize)> = Vec::with_capacity(batch_size);
+            for row in 0..rows {
+                for col in 0..columns {
+                    if let Some(values) = cell_values(row, col) {
+                        batch_data.extend(values);
+                        batch_cells.push((row, col));
+                    }
+                    let last_cell = row == rows - 1 && col == columns - 1;
+                    if batch_cells.len() == batch_size || (last_cell && !batch_cells.is_empty()) {
+                        // the last batch is padded, because the model's input shape is fixed
+                        batch_data.resize(batch_size * num_bands, 0f32);
+                        let (out, shape, _) = model.run(batch_data.clone())?;
+                        let num_outputs = match shape.len() {
+                            1 if shape[0] == batch_size => 1,
+                            2 if shape[0] == batch_size => shape[1],
+                            _ => {
+                                return Err(Error::new(
+                                    ErrorKind::InvalidInput,
+                                    format!("The model's output shape {:?} is not (batch, outputs) or (batch).", shape),
+                                ))
+                            }
+                        };
+                        check_channel(class_output, channel, num_outputs)?;
+                        for (n, &(r, c)) in batch_cells.iter().enumerate() {
+                            let scores = &out[n * num_outputs..(n + 1) * num_outputs];
+                            output.set_value(r as isize, c as isize, output_value(scores, class_output, channel));
+                        }
+                        batch_data.clear();
+                        batch_cells.clear();
+                    }
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Progress", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        } else {
+            let p = patch_size;
+            let input_shape = if channels_last {
+                [1, p, p, num_bands]
+            } else {
+                [1, num_bands, p, p]
+            };
+            let model = OnnxModel::load(&model_file, &input_shape)?;
+            let patch_rows = patch_starts(rows, p, overlap);
+            let patch_cols = patch_starts(columns, p, overlap);
+            // the blending weight of each row or column of a patch
+            let taper = (0..p)
+                .map(|i| {
+                    if overlap == 0 {
+                        1f32
+                    } else {
+                        let d = i.min(p - 1 - i) as f32 + 0.5;
+                        (d / overlap as f32).min(1f32)
+                    }
+                })
+                .collect::<Vec<f32>>();
+
+            // The accumulated outputs and weights of the rows that may still be covered by a
+            // patch, starting at row `top`; rows are written to the output as they are completed.
+            let mut top = 0usize;
+            let mut buffer: VecDeque<(Vec<f32>, Vec<f32>)> = VecDeque::new();
+            let mut num_outputs = 0usize;
+            let mut labels = false;
+            let mut patch_data = vec![0f32; num_bands * p * p];
+            for (k, &r0) in patch_rows.iter().enumerate() {
+                // rows above this patch row are complete
+                while top < r0 {
+                    if let Some((scores, weights)) = buffer.pop_front() {
+                        write_row(&mut output, top, &scores, &weights, num_outputs, labels, class_output, channel);
+                    }
+                    top += 1;
+                }
+                for &c0 in &patch_cols {
+                    let mut valid = vec![false; p * p];
+                    for v in patch_data.iter_mut() {
+                        *v = 0f32;
+                    }
+                    for i in 0..p.min(rows - r0) {
+                        for j in 0..p.min(columns - c0) {
+                            if let Some(values) = cell_values(r0 + i, c0 + j) {
+                                valid[i * p + j] = true;
+                                for (b, &z) in values.iter().enumerate() {
+                                    let idx = if channels_last {
+                                        (i * p + j) * num_bands + b
+                                    } else {
+                                        (b * p + i) * p + j
+                                    };
+                                    patch_data[idx] = z;
+                                }
+                            }
+                        }
+                    }
+                    if !valid.iter().any(|&v| v) {
+                        continue;
+                    }
+                    let (out, shape, is_integer) = model.run(patch_data.clone())?;
+                    let (n, channel_stride, cell_stride) = match shape.len() {
+                        2 if shape[0] == p && shape[1] == p => (1, 0, 1),
+                        3 if shape[0] == 1 && shape[1] == p && shape[2] == p => (1, 0, 1),
+                        4 if !channels_last && shape[2] == p && shape[3] == p => (shape[1], p * p, 1),
+                        4 if channels_last && shape[1] == p && shape[2] == p => (shape[3], 1, shape[3]),
+                        _ => {
+                            return Err(Error::new(
+                                ErrorKind::InvalidInput,
+                                format!("The model's output shape {:?} does not match its input patches.", shape),
+                            ))
+                        }
+                    };
+                    if num_outputs == 0 {
+                        num_outputs = n;
+                        labels = is_integer;
+                        check_channel(class_output, channel, num_outputs)?;
+                    }
+                    while buffer.len() < (r0 + p).min(rows) - top {
+                        buffer.push_back((vec![0f32; columns * num_outputs], vec![0f32; columns]));
+                    }
+                    for i in 0..p.min(rows - r0) {
+                        let (scores, weights) = &mut buffer[r0 + i - top];
+                        for j in 0..p.min(columns - c0) {
+                            if !valid[i * p + j] {
+                                continue;
+                            }
+                            let w = taper[i] * taper[j];
+                            let col = c0 + j;
+                            let cell = &mut scores[col * num_outputs..(col + 1) * num_outputs];
+                            if labels {
+                                // labels are taken from the patch whose centre is nearest
+                                if w > weights[col] {
+                                    cell[0] = out[(i * p + j) * cell_stride];
+                                    weights[col] = w;
+                                }
+                            } else {
+                                for (m, s) in cell.iter_mut().enumerate() {
+                                    *s += w * out[m * channel_stride + (i * p + j) * cell_stride];
+                                }
+                                weights[col] += w;
+                            }
+                        }
+                    }
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * k as f64 / (patch_rows.len() - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Progress", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            while let Some((scores, weights)) = buffer.pop_front() {
+                reporter.check_cancelled()?;
+                write_row(&mut output, top, &scores, &weights, num_outputs, labels, class_output, channel);
+                top += 1;
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Model file: {}", model_file));
+        output.add_metadata_entry(format!("Mode: {}", mode));
+        if mode == "patch" {
+            output.add_metadata_entry(format!("Patch size: {}", patch_size));
+            output.add_metadata_entry(format!("Overlap: {}", overlap));
+        }
+        output.add_metadata_entry(format!("Output type: {}", output_type));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a list of per-band values, or a single value for all bands.
+fn parse_band_values(s: &str, num_bands: usize, default: f64, flag: &str) -> Result<Vec<f64>, Error> {
+    if s.trim().is_empty() {
+        return Ok(vec![default; num_bands]);
+    }
+    let values = s
+        .split(|c| c == ',' || c == ';')
+        .map(|v| v.trim().parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, format!("Error parsing {}", flag)))?;
+    match values.len() {
+        1 => Ok(vec![values[0]; num_bands]),
+        n if n == num_bands => Ok(values),
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("The {} parameter must have one value, or one value per band.", flag),
+        )),
+    }
+}
+
+/// Returns the start of each patch along a row or column of `n` cells. The last patch is shifted
+/// so that it ends at the edge, unless there are fewer cells than the patch size.
+fn patch_starts(n: usize, patch_size: usize, overlap: usize) -> Vec<usize> {
+    if n <= patch_size {
+        return vec![0];
+    }
+    let mut starts = (0..n - patch_size).step_by(patch_size - overlap).collect::<Vec<usize>>();
+    starts.push(n - patch_size);
+    starts
+}
+
+fn check_channel(class_output: bool, channel: usize, num_outputs: usize) -> Result<(), Error> {
+    if !class_output && channel >= num_outputs {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("The --channel parameter must be less than the number of model outputs ({}).", num_outputs),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the output value of a cell from its model outputs.
+fn output_value(scores: &[f32], class_output: bool, channel: usize) -> f64 {
+    if !class_output {
+        scores[channel] as f64
+    } else if scores.len() == 1 {
+        scores[0].round() as f64
+    } else {
+        let mut class = 0;
+        for m in 1..scores.len() {
+            if scores[m] > scores[class] {
+                class = m;
+            }
+        }
+        class as f64
+    }
+}
+
+/// Writes a row of blended patch outputs to the output raster.
+fn write_row(
+    output: &mut Raster,
+    row: usize,
+    scores: &[f32],
+    weights: &[f32],
+    num_outputs: usize,
+    labels: bool,
+    class_output: bool,
+    channel: usize,
+) {
+    let mut data = vec![output.configs.nodata; weights.len()];
+    for col in 0..weights.len() {
+        if weights[col] > 0f32 {
+            let cell = &scores[col * num_outputs..(col + 1) * num_outputs];
+            data[col] = if labels {
+                output_value(cell, class_output, channel)
+            } else {
+                let blended = cell.iter().map(|s| s / weights[col]).collect::<Vec<f32>>();
+                output_value(&blended, class_output, channel)
+            };
+        }
+    }
+    output.set_row_data(row as isize, data);
+}
+
+#[cfg(feature = "onnx")]
+mod onnx_model {
+    use std::io::{Error, ErrorKind};
+    use tract_onnx::prelude::*;
+
+    /// An ONNX model, optimized for an input of a fixed shape.
+    pub struct OnnxModel {
+        plan: TypedRunnableModel<TypedModel>,
+        input_shape: Vec<usize>,
+    }
+
+    impl OnnxModel {
+        pub fn load(file_name: &str, input_shape: &[usize]) -> Result<OnnxModel, Error> {
+            let plan = tract_onnx::onnx()
+                .model_for_path(file_name)
+                .and_then(|model| {
+                    model.with_input_fact(
+                        0,
+                        InferenceFact::dt_shape(f32::datum_type(), input_shape.to_vec()),
+                    )
+                })
+                // the declared output shape may have a symbolic batch dimension, which would
+                // conflict with the fixed input shape, and so it is inferred instead
+                .and_then(|model| model.with_output_fact(0, InferenceFact::default()))
+                .and_then(|model| model.into_optimized())
+                .and_then(|model| model.into_runnable())
+                .map_err(|e| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Error loading the ONNX model with the input shape {:?}: {:?}", input_shape, e),
+                    )
+                })?;
+            Ok(OnnxModel {
+                plan,
+                input_shape: input_shape.to_vec(),
+            })
+        }
+
+        /// Runs the model and returns its first output, converted to 32-bit floats, the shape of
+        /// the output, and whether its values are integers.
+        pub fn run(&self, data: Vec<f32>) -> Result<(Vec<f32>, Vec<usize>, bool), Error> {
+            let model_error = |e: TractError| {
+                Error::new(ErrorKind::Other, format!("Error running the ONNX model: {:?}", e))
+            };
+            let input = Tensor::from_shape(&self.input_shape, &data).map_err(model_error)?;
+            let outputs = self.plan.run(tvec!(input.into())).map_err(model_error)?;
+            let output = &outputs[0];
+            let is_integer = output.datum_type().is_integer();
+            let values = output.cast_to::<f32>().map_err(model_error)?;
+            let values = values.as_slice::<f32>().map_err(model_error)?.to_vec();
+            Ok((values, output.shape().to_vec(), is_integer))
+        }
+    }
+}
+
+#[cfg(not(feature = "onnx"))]
+mod onnx_model {
+    use std::io::{Error, ErrorKind};
+
+    pub struct OnnxModel;
+
+    impl OnnxModel {
+        pub fn load(_file_name: &str, _input_shape: &[usize]) -> Result<OnnxModel, Error> {
+            Err(Error::new(
+                ErrorKind::Other,
+                "This build of WhiteboxTools does not support ONNX models; it must be built with the 'onnx' feature (cargo build --release --features onnx).",
+            ))
+        }
+
+        pub fn run(&self, _data: Vec<f32>) -> Result<(Vec<f32>, Vec<usize>, bool), Error> {
+            unreachable!()
+        }
+    }
+}
@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// Panchromatic sharpening, or simply pan-sharpening, refers to a range of techniques that can be used to merge
 /// finer spatial resolution panchromatic images with coarser spatial resolution multi-spectral images. The
@@ -55,7 +54,7 @@ impl PanchromaticSharpening {
         parameters.push(ToolParameter {
             name: "Input Red Band File (optional; only if colour-composite not specified)"
                 .to_owned(),
-            flags: vec!["--red".to_owned()],
+            flags: vec!["--r".to_owned(), "--red".to_owned()],
             description:
                 "Input red band image file. Optionally specified if colour-composite not specified."
                     .to_owned(),
@@ -66,7 +65,7 @@ impl PanchromaticSharpening {
 
         parameters.push(ToolParameter{
             name: "Input Green Band File (optional; only if colour-composite not specified)".to_owned(), 
-            flags: vec!["--green".to_owned()], 
+            flags: vec!["--g".to_owned(), "--green".to_owned()], 
             description: "Input green band image file. Optionally specified if colour-composite not specified.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -75,7 +74,7 @@ impl PanchromaticSharpening {
 
         parameters.push(ToolParameter{
             name: "Input Blue Band File (optional; only if colour-composite not specified)".to_owned(), 
-            flags: vec!["--blue".to_owned()], 
+            flags: vec!["--b".to_owned(), "--blue".to_owned()], 
             description: "Input blue band image file. Optionally specified if colour-composite not specified.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -84,7 +83,7 @@ impl PanchromaticSharpening {
 
         parameters.push(ToolParameter{
             name: "Input Colour-Composite Image File (optional; only if individual bands not specified)".to_owned(), 
-            flags: vec!["--composite".to_owned()], 
+            flags: vec!["--c".to_owned(), "--composite".to_owned()], 
             description: "Input colour-composite image file. Only used if individual bands are not specified.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -93,7 +92,7 @@ impl PanchromaticSharpening {
 
         parameters.push(ToolParameter {
             name: "Input Panchromatic Band File".to_owned(),
-            flags: vec!["--pan".to_owned()],
+            flags: vec!["--p".to_owned(), "--pan".to_owned()],
             description: "Input panchromatic band file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -184,81 +183,29 @@ impl WhiteboxTool for PanchromaticSharpening {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut red_file = String::new();
-        let mut green_file = String::new();
-        let mut blue_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut red_file = parsed.get_string("red").unwrap_or_default();
+        let mut green_file = parsed.get_string("green").unwrap_or_default();
+        let mut blue_file = parsed.get_string("blue").unwrap_or_default();
         let mut composite_file = String::new();
         let mut use_composite = false;
-        let mut pan_file = String::new();
-        let mut output_file = String::new();
+        let mut pan_file = parsed.get_string("pan").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut fusion_method = String::from("brovey");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_string("composite") {
+            composite_file = value;
+            use_composite = true;
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-r" || flag_val == "-red" {
-                red_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-g" || flag_val == "-green" {
-                green_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-b" || flag_val == "-blue" {
-                blue_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-p" || flag_val == "-pan" {
-                pan_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-c" || flag_val == "-composite" {
-                composite_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                use_composite = true;
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-method" {
-                fusion_method = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                fusion_method = if fusion_method.to_lowercase().contains("bro") {
-                    String::from("brovey")
-                } else {
-                    String::from("ihs")
-                };
-            }
+        if let Some(value) = parsed.get_string("method") {
+            fusion_method = value;
+            fusion_method = if fusion_method.to_lowercase().contains("bro") {
+                String::from("brovey")
+            } else {
+                String::from("ihs")
+            };
         }
 
         if verbose {
@@ -272,36 +219,18 @@ impl WhiteboxTool for PanchromaticSharpening {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !red_file.contains(&sep) && !red_file.contains("/") {
-            red_file = format!("{}{}", working_directory, red_file);
-        }
-        if !green_file.contains(&sep) && !green_file.contains("/") {
-            green_file = format!("{}{}", working_directory, green_file);
-        }
-        if !blue_file.contains(&sep) && !blue_file.contains("/") {
-            blue_file = format!("{}{}", working_directory, blue_file);
-        }
-        if !composite_file.contains(&sep) && !composite_file.contains("/") {
-            composite_file = format!("{}{}", working_directory, composite_file);
-        }
-        if !pan_file.contains(&sep) && !pan_file.contains("/") {
-            pan_file = format!("{}{}", working_directory, pan_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        red_file = resolve_input_path(&red_file, working_directory)?;
+        green_file = resolve_input_path(&green_file, working_directory)?;
+        blue_file = resolve_input_path(&blue_file, working_directory)?;
+        composite_file = resolve_input_path(&composite_file, working_directory)?;
+        pan_file = resolve_input_path(&pan_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         let mut input: Array2D<f64>;
         let rows_ms: isize;
@@ -346,15 +275,15 @@ impl WhiteboxTool for PanchromaticSharpening {
             if input_r.configs.rows != input_g.configs.rows
                 || input_r.configs.columns != input_g.configs.columns
             {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                      "The input files must have the same number of rows and columns and spatial extent."));
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
             }
+            warn_if_crs_differs(&input_r, &input_g, verbose);
             if input_r.configs.rows != input_b.configs.rows
                 || input_r.configs.columns != input_b.configs.columns
             {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                      "The input files must have the same number of rows and columns and spatial extent."));
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
             }
+            warn_if_crs_differs(&input_r, &input_b, verbose);
 
             let nodata_r = input_r.configs.nodata;
             let nodata_g = input_g.configs.nodata;
@@ -378,11 +307,12 @@ impl WhiteboxTool for PanchromaticSharpening {
             let b_min = input_b.configs.display_min;
             let b_range = input_b.configs.display_max - input_b.configs.display_min;
             for row in 0..rows_ms {
+                reporter.check_cancelled()?;
                 for col in 0..columns_ms {
                     r = input_r[(row, col)];
                     g = input_g[(row, col)];
                     b = input_b[(row, col)];
-                    if r != nodata_r && g != nodata_g && b != nodata_b {
+                    if !is_nodata(r, nodata_r) && !is_nodata(g, nodata_g) && !is_nodata(b, nodata_b) {
                         r = (r - r_min) / r_range * 255f64;
                         if r < 0f64 {
                             r = 0f64;
@@ -443,7 +373,7 @@ impl WhiteboxTool for PanchromaticSharpening {
                 let pan = pan.clone();
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let get_column_from_x =
                         |x: f64| -> isize { ((x - west) / resolution_x).floor() as isize };
                     let get_row_from_y =
@@ -465,7 +395,7 @@ impl WhiteboxTool for PanchromaticSharpening {
                             z_pan = pan[(row, col)];
                             z_ms = input[(source_row, source_col)];
 
-                            if z_ms != nodata_ms && z_pan != nodata_pan {
+                            if !is_nodata(z_ms, nodata_ms) && !is_nodata(z_pan, nodata_pan) {
                                 p = (z_pan - pan_min) / pan_range;
                                 if p < 0f64 {
                                     p = 0f64;
@@ -498,7 +428,9 @@ impl WhiteboxTool for PanchromaticSharpening {
                                     ((255 << 24) | (b_out << 16) | (g_out << 8) | r_out) as f64;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -506,10 +438,11 @@ impl WhiteboxTool for PanchromaticSharpening {
             for row in 0..rows_pan {
                 let data = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(data.0, data.1);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * (row + 1) as f64 / rows_pan as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -519,17 +452,22 @@ impl WhiteboxTool for PanchromaticSharpening {
 
             // find the overall maximum in the ms data
             let (tx, rx) = mpsc::channel();
+            let token = reporter.cancellation_token();
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                let token = token.clone();
+                spawn(move || {
                     let mut overall_max = f64::NEG_INFINITY;
                     let (mut r, mut g, mut b): (f64, f64, f64);
                     let mut z: f64;
                     for row in (0..rows_ms).filter(|row_val| row_val % num_procs == tid) {
+                        if token.is_cancelled() {
+                            break;
+                        }
                         for col in 0..columns_ms {
                             z = input[(row, col)];
-                            if z != nodata_ms {
+                            if !is_nodata(z, nodata_ms) {
                                 r = (z as u32 & 0xFF) as f64;
                                 g = ((z as u32 >> 8) & 0xFF) as f64;
                                 b = ((z as u32 >> 16) & 0xFF) as f64;
@@ -546,7 +484,9 @@ impl WhiteboxTool for PanchromaticSharpening {
                             }
                         }
                     }
-                    tx.send(overall_max).unwrap();
+                    if tx.send(overall_max).is_err() {
+                        return;
+                    }
                 });
             }
 
@@ -556,10 +496,11 @@ impl WhiteboxTool for PanchromaticSharpening {
                 if data > overall_max {
                     overall_max = data;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * tid as f64 / (num_procs - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -570,7 +511,7 @@ impl WhiteboxTool for PanchromaticSharpening {
                 let pan = pan.clone();
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let get_column_from_x =
                         |x: f64| -> isize { ((x - west) / resolution_x).floor() as isize };
                     let get_row_from_y =
@@ -592,7 +533,7 @@ impl WhiteboxTool for PanchromaticSharpening {
                             source_col = get_column_from_x(x);
                             z_pan = pan[(row, col)];
                             z_ms = input[(source_row, source_col)];
-                            if z_ms != nodata_ms && z_pan != nodata_pan {
+                            if !is_nodata(z_ms, nodata_ms) && !is_nodata(z_pan, nodata_pan) {
                                 p = (z_pan - pan_min) / pan_range;
                                 if p < 0f64 {
                                     p = 0f64;
@@ -672,7 +613,9 @@ impl WhiteboxTool for PanchromaticSharpening {
                                     ((255 << 24) | (b_out << 16) | (g_out << 8) | r_out) as f64;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -680,10 +623,11 @@ impl WhiteboxTool for PanchromaticSharpening {
             for row in 0..rows_pan {
                 let data = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(data.0, data.1);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows_pan - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
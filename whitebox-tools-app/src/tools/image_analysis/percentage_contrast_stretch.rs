@@ -12,7 +12,6 @@ NOTES: 1. The tool should be updated to take multiple file inputs.
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
@@ -20,7 +19,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a percentage contrast stretch on a raster image. This operation maps each grid cell value
 /// in the input raster image (z<sub>in</sub>) onto a new scale that ranges from a lower-tail clip value (`min_val`)
@@ -89,7 +88,7 @@ impl PercentageContrastStretch {
 
         parameters.push(ToolParameter{
             name: "Tail".to_owned(), 
-            flags: vec!["--tail".to_owned()], 
+            flags: vec!["--tails".to_owned(), "--tail".to_owned()], 
             description: "Specified which tails to clip; options include 'upper', 'lower', and 'both' (default is 'both').".to_owned(),
             parameter_type: ParameterType::OptionList(vec!["upper".to_owned(), "lower".to_owned(), "both".to_owned()]),
             default_value: Some("both".to_owned()),
@@ -170,78 +169,23 @@ impl WhiteboxTool for PercentageContrastStretch {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut tail = String::from("both");
-        let mut clip = f64::NEG_INFINITY;
-        let mut num_tones = 256f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-clip" {
-                if keyval {
-                    clip = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    clip = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-tail" || flag_val == "-tails" {
-                if keyval {
-                    tail = vec[1].to_string();
-                } else {
-                    tail = args[i + 1].to_string();
-                }
-                if tail.to_lowercase().contains("u") {
-                    tail = String::from("upper");
-                } else if tail.to_lowercase().contains("l") {
-                    tail = String::from("lower");
-                } else {
-                    tail = String::from("both");
-                }
-            } else if flag_val == "-num_tones" {
-                if keyval {
-                    num_tones = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    num_tones = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
+        let clip = parsed.get_f64("clip").unwrap_or(1f64);
+        let mut num_tones = parsed.get_f64("num_tones").unwrap_or(256f64);
+
+        if let Some(value) = parsed.get_string("tail") {
+            tail = value;
+            if tail.to_lowercase().contains("u") {
+                tail = String::from("upper");
+            } else if tail.to_lowercase().contains("l") {
+                tail = String::from("lower");
+            } else {
+                tail = String::from("both");
             }
         }
 
@@ -256,17 +200,11 @@ impl WhiteboxTool for PercentageContrastStretch {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if num_tones < 16f64 {
             println!("Warning: The output number of greytones must be at least 16. The value has been modified.");
@@ -344,7 +282,7 @@ impl WhiteboxTool for PercentageContrastStretch {
             for row in 0..rows {
                 for col in 0..columns {
                     value = input.get_value(row, col);
-                    if value != nodata {
+                    if !is_nodata(value, nodata) {
                         x = value2i(value); // gets the intensity
                         if x < min_val {
                             min_val = x;
@@ -357,10 +295,11 @@ impl WhiteboxTool for PercentageContrastStretch {
                         histo[bin] += 1usize;
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Calculating clip values: {}%", progress);
+                        reporter.progress("Calculating clip values", progress);
                         old_progress = progress;
                     }
                 }
@@ -409,23 +348,19 @@ impl WhiteboxTool for PercentageContrastStretch {
             ));
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
                     Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
                 } else {
                     Box::new(|row: isize, col: isize| -> f64 {
                         let value = input.get_value(row, col);
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             let v = value2i(value);
                             return v;
                         }
@@ -437,7 +372,7 @@ impl WhiteboxTool for PercentageContrastStretch {
                     Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
                 } else {
                     Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
+                        if !is_nodata(value, nodata) {
                             let (h, s, _) = value2hsi(input.get_value(row, col));
                             let ret = hsi2value(h, s, value / num_tones);
                             return ret;
@@ -452,7 +387,7 @@ impl WhiteboxTool for PercentageContrastStretch {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z_in = input_fn(row, col);
-                        if z_in != nodata {
+                        if !is_nodata(z_in, nodata) {
                             z_out = ((z_in - min_val) / value_range * num_tones).floor();
                             if z_out < 0f64 {
                                 z_out = 0f64;
@@ -463,7 +398,9 @@ impl WhiteboxTool for PercentageContrastStretch {
                             data[col as usize] = output_fn(row, col, z_out);
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -472,10 +409,11 @@ impl WhiteboxTool for PercentageContrastStretch {
         for r in 0..rows {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,525 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::rendering::*;
+use whitebox_raster::*;
+use crate::tools::*;
+use whitebox_vector::{ShapeType, Shapefile};
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// The colours in which the features of the overlaid vectors are drawn, in turn.
+const OVERLAY_COLOURS: [Rgba; 6] = [
+    [230, 25, 75, 255],
+    [0, 0, 0, 255],
+    [0, 130, 200, 255],
+    [255, 225, 25, 255],
+    [240, 50, 230, 255],
+    [60, 180, 75, 255],
+];
+
+/// This tool renders a small preview image (PNG) of a raster, and/or of vector overlays, for checking
+/// the inputs and outputs of a workflow without loading them into GIS software. It is used by the
+/// Whitebox Runner to display datasets after a tool completes, but may also be run directly.
+///
+/// The raster (`--input`) is read at a reduced resolution, such that neither dimension of the image is
+/// much larger than `--max_size` pixels (512 by default). If the raster is a GeoTIFF with overviews
+/// (pyramids), e.g. a cloud-optimized GeoTIFF, the smallest overview that is large enough is read
+/// instead of the full-resolution image, which keeps previews of large or remote rasters fast;
+/// otherwise the raster is read and resampled by nearest neighbour. The values are coloured using a
+/// colour ramp (`--palette`), either one of the built-in ramps, `grey`, `viridis`, `magma`, `terrain`,
+/// `spectral`, `blue_white_red`, `brown_green` and `blues`, or a palette file, as in the
+/// `ColourizeRaster` tool. The ramp spans the range of values after clipping the percentage `--clip` of
+/// the cells from each tail of the distribution. The colours may be blended with a hillshade of the
+/// raster (`--hillshade`), by the `--blend` weight (0-1), to show the form of a terrain surface. NoData
+/// cells are transparent.
+///
+/// Any number of vector files (`--vectors`) may be drawn over the raster, each in its own colour,
+/// with lines and polygon outlines drawn one pixel wide and points as small squares. Vectors are not
+/// reprojected and must share the coordinate reference system of the raster. If no raster is given,
+/// the image covers the combined extent of the vectors, on a transparent background.
+///
+/// The output PNG (`--output`) is georeferenced by a world file (.pgw).
+///
+/// # See Also
+/// `ColourizeRaster`, `ExportWebTiles`, `Hillshade`
+pub struct QuickView {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl QuickView {
+    pub fn new() -> QuickView {
+        // public constructor
+        let name = "QuickView".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Renders a small preview image (PNG) of a raster, optionally hillshaded, with vector overlays."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Raster File (optional)".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Vector Files (optional)".to_owned(),
+            flags: vec!["--vectors".to_owned()],
+            description: "Input vector files drawn over the raster.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output PNG (.png) file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Palette".to_owned(),
+            flags: vec!["--palette".to_owned()],
+            description: "Built-in colour ramp name (e.g. 'viridis', 'terrain') or palette file.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("viridis".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reverse palette?".to_owned(),
+            flags: vec!["--reverse".to_owned()],
+            description: "Optional flag to reverse the colours of the palette.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Tail Clip Percent".to_owned(),
+            flags: vec!["--clip".to_owned()],
+            description: "Percentage of cells clipped from each tail of the distribution to find the range of values.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Blend with a hillshade?".to_owned(),
+            flags: vec!["--hillshade".to_owned()],
+            description: "Optional flag to blend the colours with a hillshade of the raster.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Hillshade Blend Weight".to_owned(),
+            flags: vec!["--blend".to_owned()],
+            description: "Weight (0-1) of the hillshade in the blended image.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Image Size".to_owned(),
+            flags: vec!["--max_size".to_owned()],
+            description: "Maximum width and height of the image, in pixels.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("512".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=dem.tif --vectors='streams.shp;basins.shp' -o=preview.png --palette=terrain --hillshade", short_exe, name).replace("*", &sep);
+
+        QuickView {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for QuickView {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_file = parsed.get_string("input").unwrap_or_default();
+        let vector_files = parsed.get_list("vectors");
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let palette = parsed.get_string("palette").unwrap_or("viridis".to_string());
+        let reverse = parsed.get_bool("reverse");
+        let clip = parsed.get_f64("clip").unwrap_or(1f64);
+        let hillshade = parsed.get_bool("hillshade");
+        let blend = parsed.get_f64("blend").unwrap_or(0.5f64);
+        let max_size = parsed.get_usize("max_size")?.unwrap_or(512);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if input_file.is_empty() && vector_files.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one of an input raster and input vectors must be specified.",
+            ));
+        }
+        if !output_file.to_lowercase().ends_with(".png") {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The output file must be a PNG image (.png).",
+            ));
+        }
+        if clip < 0f64 || clip >= 50f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The tail clip percent must be between 0 and 50.",
+            ));
+        }
+        if blend < 0f64 || blend > 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The hillshade blend weight must be between 0 and 1.",
+            ));
+        }
+        if max_size < 16 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum image size must be at least 16 pixels.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        output_file = resolve_path(&output_file, working_directory);
+        let mut vectors = Vec::with_capacity(vector_files.len());
+        for file in &vector_files {
+            let file = resolve_input_path(file, working_directory)?;
+            vectors.push(Shapefile::read(&file)?);
+        }
+        let raster = if !input_file.is_empty() {
+            let input_file = resolve_input_path(&input_file, working_directory)?;
+            Some(Raster::new_overview(&input_file, max_size)?)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        // The grid of the image, which is either that of the (reduced-resolution) raster or one
+        // that covers the vectors.
+        let base = match &raster {
+            Some(r) => Raster::initialize_using_file(&output_file, r),
+            None => {
+                let (mut west, mut east) = (f64::INFINITY, f64::NEG_INFINITY);
+                let (mut south, mut north) = (f64::INFINITY, f64::NEG_INFINITY);
+                for sf in &vectors {
+                    west = west.min(sf.header.x_min);
+                    east = east.max(sf.header.x_max);
+                    south = south.min(sf.header.y_min);
+                    north = north.max(sf.header.y_max);
+                }
+                if !(west <= east && south <= north) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "The input vectors do not contain any features.",
+                    ));
+                }
+                // pad the extent such that features on its edges are visible
+                let pad = 0.02 * (east - west).max(north - south).max(f64::EPSILON);
+                west -= pad;
+                east += pad;
+                south -= pad;
+                north += pad;
+                let cell_size = (east - west).max(north - south) / max_size as f64;
+                let mut configs = RasterConfigs {
+                    ..Default::default()
+                };
+                configs.columns = (((east - west) / cell_size).round() as usize).max(1);
+                configs.rows = (((north - south) / cell_size).round() as usize).max(1);
+                configs.north = north;
+                configs.south = north - configs.rows as f64 * cell_size;
+                configs.west = west;
+                configs.east = west + configs.columns as f64 * cell_size;
+                configs.resolution_x = cell_size;
+                configs.resolution_y = cell_size;
+                configs.nodata = -32768f64;
+                configs.set_crs(&whitebox_common::spatial_ref_system::CoordinateReferenceSystem::from_wkt(&vectors[0].projection));
+                Raster::initialize_using_config(&output_file, &configs)
+            }
+        };
+        let rows = base.configs.rows as isize;
+        let columns = base.configs.columns as isize;
+        let mut pixels: Vec<Rgba> = vec![TRANSPARENT; (rows * columns) as usize];
+
+        if let Some(input) = &raster {
+            let mut ramp = ColourRamp::new(&palette, working_directory)?;
+            if reverse {
+                ramp.reverse();
+            }
+            let nodata_colour = ramp.nodata_colour();
+            let nodata = input.configs.nodata;
+            let (low, high) = input.calculate_clip_values(clip);
+            let stops = ramp.resolve(low, high);
+
+            // Converts the elevation differences of geographic rasters to the units of their
+            // cell sizes, i.e. degrees.
+            let z_factor = if input.is_in_geographic_coordinates() {
+                let mid_lat = ((input.configs.north + input.configs.south) / 2f64).to_radians();
+                1f64 / (111320f64 * mid_lat.cos())
+            } else {
+                1f64
+            };
+            // The direction of the light, from the northwest at an altitude of 30 degrees.
+            let (azimuth, altitude) = (315f64.to_radians(), 30f64.to_radians());
+            let light = [
+                azimuth.sin() * altitude.cos(),
+                azimuth.cos() * altitude.cos(),
+                altitude.sin(),
+            ];
+            let eight_res_x = 8f64 * input.configs.resolution_x;
+            let eight_res_y = 8f64 * input.configs.resolution_y;
+
+            for row in 0..rows {
+                for col in 0..columns {
+                    let z = input.get_value(row, col);
+                    let i = (row * columns + col) as usize;
+                    if is_nodata(z, nodata) {
+                        pixels[i] = nodata_colour;
+                        continue;
+                    }
+                    let mut c = ramp_colour(&stops, z);
+                    if hillshade {
+                        // the 3x3 neighbourhood, row by row, with NoData neighbours replaced by
+                        // the centre value
+                        let mut n = [z; 9];
+                        for k in 0..9 {
+                            let v = input.get_value(row + k as isize / 3 - 1, col + k as isize % 3 - 1);
+                            if !is_nodata(v, nodata) {
+                                n[k] = v;
+                            }
+                        }
+                        let dz_dx = ((n[2] + 2f64 * n[5] + n[8]) - (n[0] + 2f64 * n[3] + n[6]))
+                            / eight_res_x
+                            * z_factor;
+                        let dz_dy = ((n[0] + 2f64 * n[1] + n[2]) - (n[6] + 2f64 * n[7] + n[8]))
+                            / eight_res_y
+                            * z_factor;
+                        let norm = (dz_dx * dz_dx + dz_dy * dz_dy + 1f64).sqrt();
+                        let shade = ((-dz_dx * light[0] - dz_dy * light[1] + light[2]) / norm)
+                            .max(0f64);
+                        let factor = 1f64 - blend + blend * shade;
+                        for k in 0..3 {
+                            c[k] *= factor;
+                        }
+                    }
+                    pixels[i] = [
+                        c[0].round() as u8,
+                        c[1].round() as u8,
+                        c[2].round() as u8,
+                        c[3].round() as u8,
+                    ];
+                }
+                reporter.check_cancelled()?;
+            }
+        }
+
+        // Draw the vector overlays, converting coordinates to (fractional) columns and rows.
+        let (west, north) = (base.configs.west, base.configs.north);
+        let (res_x, res_y) = (base.configs.resolution_x, base.configs.resolution_y);
+        let to_image = |x: f64, y: f64| ((x - west) / res_x - 0.5, (north - y) / res_y - 0.5);
+        for (v, sf) in vectors.iter().enumerate() {
+            let colour = OVERLAY_COLOURS[v % OVERLAY_COLOURS.len()];
+            let is_point_type = matches!(
+                sf.header.shape_type.base_shape_type(),
+                ShapeType::Point | ShapeType::MultiPoint
+            );
+            for record_num in 0..sf.num_records {
+                let record = sf.get_record(record_num);
+                if is_point_type {
+                    for p in &record.points {
+                        let (c, r) = to_image(p.x, p.y);
+                        let (c, r) = (c.round() as isize, r.round() as isize);
+                        for dr in -1..=1 {
+                            for dc in -1..=1 {
+                                set_pixel(&mut pixels, rows, columns, r + dr, c + dc, colour);
+                            }
+                        }
+                    }
+                    continue;
+                }
+                for part in 0..record.num_parts as usize {
+                    let first = record.parts[part] as usize;
+                    let last = if part < record.num_parts as usize - 1 {
+                        record.parts[part + 1] as usize
+                    } else {
+                        record.num_points as usize
+                    };
+                    for i in first + 1..last {
+                        let p0 = to_image(record.points[i - 1].x, record.points[i - 1].y);
+                        let p1 = to_image(record.points[i].x, record.points[i].y);
+                        draw_line(&mut pixels, rows, columns, p0, p1, colour);
+                    }
+                }
+            }
+            reporter.check_cancelled()?;
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match write_image(&output_file, &base, &pixels, vec![]) {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn set_pixel(pixels: &mut [Rgba], rows: isize, columns: isize, row: isize, col: isize, colour: Rgba) {
+    if row >= 0 && row < rows && col >= 0 && col < columns {
+        pixels[(row * columns + col) as usize] = colour;
+    }
+}
+
+/// Draws a one-pixel-wide line between two points, given as (column, row), after clipping it to
+/// the image (Liang-Barsky), such that segments extending far beyond the image are cheap to draw.
+fn draw_line(
+    pixels: &mut [Rgba],
+    rows: isize,
+    columns: isize,
+    p0: (f64, f64),
+    p1: (f64, f64),
+    colour: Rgba,
+) {
+    let (dx, dy) = (p1.0 - p0.0, p1.1 - p0.1);
+    let (mut t0, mut t1) = (0f64, 1f64);
+    let bounds = [
+        (-dx, p0.0 + 1f64),
+        (dx, columns as f64 - p0.0),
+        (-dy, p0.1 + 1f64),
+        (dy, rows as f64 - p0.1),
+    ];
+    for &(p, q) in &bounds {
+        if p == 0f64 {
+            if q < 0f64 {
+                return;
+            }
+        } else {
+            let t = q / p;
+            if p < 0f64 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 > t1 {
+        return;
+    }
+    let (x0, y0) = (p0.0 + t0 * dx, p0.1 + t0 * dy);
+    let (x1, y1) = (p0.0 + t1 * dx, p0.1 + t1 * dy);
+    let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1f64) as usize;
+    for s in 0..=steps {
+        let t = s as f64 / steps as f64;
+        let col = (x0 + t * (x1 - x0)).round() as isize;
+        let row = (y0 + t * (y1 - y0)).round() as isize;
+        set_pixel(pixels, rows, columns, row, col, colour);
+    }
+}
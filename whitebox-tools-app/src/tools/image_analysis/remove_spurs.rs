@@ -11,14 +11,13 @@ and written to during the same loop. Doing so would involve using a mutex.
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This image processing tool removes small irregularities (i.e. spurs) on the boundaries of objects in a
 /// Boolean input raster image (`--input`). This operation is sometimes called *pruning*. Remove Spurs is a useful tool
@@ -143,53 +142,12 @@ impl WhiteboxTool for RemoveSpurs {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut max_iterations = 10;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-iterations" {
-                max_iterations = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let max_iterations = parsed.get_usize("iterations")?.unwrap_or(10);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -202,17 +160,11 @@ impl WhiteboxTool for RemoveSpurs {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -225,27 +177,25 @@ impl WhiteboxTool for RemoveSpurs {
 
         let start = Instant::now();
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
-                        if input[(row, col)] > 0.0 && input[(row, col)] != nodata {
+                        if input[(row, col)] > 0.0 && !is_nodata(input[(row, col)], nodata) {
                             data[col as usize] = 1.0;
                         } else if input[(row, col)] == 0.0 {
                             data[col as usize] = 0.0;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -255,10 +205,11 @@ impl WhiteboxTool for RemoveSpurs {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Initializing output: {}%", progress);
+                    reporter.progress("Initializing output", progress);
                     old_progress = progress;
                 }
             }
@@ -289,7 +240,7 @@ impl WhiteboxTool for RemoveSpurs {
                     for row in 0..rows {
                         for col in 0..columns {
                             z = output[(row, col)];
-                            if z > 0.0 && z != nodata {
+                            if z > 0.0 && !is_nodata(z, nodata) {
                                 // fill the neighbours array
                                 for i in 0..8 {
                                     neighbours[i] = output[(row + dy[i], col + dx[i])];
@@ -309,10 +260,11 @@ impl WhiteboxTool for RemoveSpurs {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * a as f64 / 7.0) as usize;
                         if progress != old_progress {
-                            println!("Loop Number {}: {}%", loop_num + 1, progress);
+                            reporter.progress(&format!("Loop Number {}", loop_num + 1), progress);
                             old_progress = progress;
                         }
                     }
@@ -322,7 +274,7 @@ impl WhiteboxTool for RemoveSpurs {
                     for row in (0..rows).rev() {
                         for col in (0..columns).rev() {
                             z = output[(row, col)];
-                            if z > 0.0 && z != nodata {
+                            if z > 0.0 && !is_nodata(z, nodata) {
                                 // fill the neighbours array
                                 for i in 0..8 {
                                     neighbours[i] = output[(row + dy[i], col + dx[i])];
@@ -342,10 +294,11 @@ impl WhiteboxTool for RemoveSpurs {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * a as f64 / 7.0) as usize;
                         if progress != old_progress {
-                            println!("Loop Number {}: {}%", loop_num + 1, progress);
+                            reporter.progress(&format!("Loop Number {}", loop_num + 1), progress);
                             old_progress = progress;
                         }
                     }
@@ -0,0 +1,325 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+// The colour ramps, contrast stretches and image output shared by the ColourizeRaster,
+// ExportColourComposite, ExportWebTiles and QuickView tools.
+
+use whitebox_raster::*;
+use whitebox_raster::png;
+use whitebox_common::utils::resolve_input_path;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+/// An 8-bit red, green, blue and alpha colour.
+pub(super) type Rgba = [u8; 4];
+
+/// The colour of NoData cells, which are fully transparent.
+pub(super) const TRANSPARENT: Rgba = [0, 0, 0, 0];
+
+/// The names of the built-in colour ramps.
+pub(super) const RAMP_NAMES: [&str; 8] = [
+    "grey",
+    "viridis",
+    "magma",
+    "terrain",
+    "spectral",
+    "blue_white_red",
+    "brown_green",
+    "blues",
+];
+
+/// Returns the stops of a built-in colour ramp, as fractions (0-1) of the range of values and
+/// colours, or None if there is no ramp of that name.
+fn built_in_ramp(name: &str) -> Option<Vec<(f64, [u8; 3])>> {
+    let stops = match name {
+        "grey" => vec![(0.0, [0, 0, 0]), (1.0, [255, 255, 255])],
+        "viridis" => vec![
+            (0.0, [68, 1, 84]),
+            (0.25, [59, 82, 139]),
+            (0.5, [33, 145, 140]),
+            (0.75, [94, 201, 98]),
+            (1.0, [253, 231, 37]),
+        ],
+        "magma" => vec![
+            (0.0, [0, 0, 4]),
+            (0.25, [81, 18, 124]),
+            (0.5, [183, 55, 121]),
+            (0.75, [252, 137, 97]),
+            (1.0, [252, 253, 191]),
+        ],
+        "terrain" => vec![
+            (0.0, [38, 115, 0]),
+            (0.25, [139, 181, 74]),
+            (0.5, [238, 220, 130]),
+            (0.75, [168, 112, 0]),
+            (0.9, [130, 130, 130]),
+            (1.0, [255, 255, 255]),
+        ],
+        "spectral" => vec![
+            (0.0, [158, 1, 66]),
+            (0.25, [244, 109, 67]),
+            (0.5, [255, 255, 191]),
+            (0.75, [102, 194, 165]),
+            (1.0, [94, 79, 162]),
+        ],
+        "blue_white_red" => vec![
+            (0.0, [5, 48, 97]),
+            (0.5, [247, 247, 247]),
+            (1.0, [103, 0, 31]),
+        ],
+        "brown_green" => vec![
+            (0.0, [166, 97, 26]),
+            (0.5, [255, 255, 191]),
+            (1.0, [26, 150, 65]),
+        ],
+        "blues" => vec![(0.0, [247, 251, 255]), (1.0, [8, 48, 107])],
+        _ => return None,
+    };
+    Some(stops)
+}
+
+/// A colour ramp, i.e. a sequence of colours at either absolute values or at fractions of the
+/// range of values being rendered, between which colours are interpolated linearly.
+pub(super) struct ColourRamp {
+    // (position, is the position a fraction of the range?, colour)
+    stops: Vec<(f64, bool, [f64; 4])>,
+    nodata: Rgba,
+}
+
+impl ColourRamp {
+    /// Creates either a built-in colour ramp, given its name, or a ramp read from a palette file.
+    ///
+    /// Palette files use the format of GDAL's color-relief palettes: each line contains a value
+    /// followed by red, green, blue and optionally alpha values (0-255), separated by spaces, tabs,
+    /// commas or colons. A value ending with '%' is a percentage of the range of values being
+    /// rendered, and the value 'nv' sets the colour of NoData cells. Lines starting with '#' are
+    /// comments.
+    pub(super) fn new(palette: &str, working_directory: &str) -> Result<ColourRamp, Error> {
+        if let Some(stops) = built_in_ramp(&palette.to_lowercase()) {
+            return Ok(ColourRamp {
+                stops: stops
+                    .into_iter()
+                    .map(|(p, c)| (p, true, [c[0] as f64, c[1] as f64, c[2] as f64, 255f64]))
+                    .collect(),
+                nodata: TRANSPARENT,
+            });
+        }
+        let file_name = resolve_input_path(palette, working_directory).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "The palette '{}' is neither a built-in colour ramp ({}) nor an existing palette file.",
+                    palette,
+                    RAMP_NAMES.join(", ")
+                ),
+            )
+        })?;
+        let contents = fs::read_to_string(&file_name)?;
+        let mut stops = vec![];
+        let mut nodata = TRANSPARENT;
+        for (line_num, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_line = || {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Error reading line {} of palette file {}.", line_num + 1, file_name),
+                )
+            };
+            let fields: Vec<&str> = line
+                .split(|c: char| c.is_whitespace() || c == ',' || c == ':')
+                .filter(|s| !s.is_empty())
+                .collect();
+            if fields.len() < 4 {
+                return Err(bad_line());
+            }
+            let mut colour = [255f64; 4];
+            for i in 1..fields.len().min(5) {
+                let v = fields[i].parse::<f64>().map_err(|_| bad_line())?;
+                colour[i - 1] = v.max(0f64).min(255f64);
+            }
+            let value = fields[0].to_lowercase();
+            if value == "nv" {
+                nodata = [
+                    colour[0] as u8,
+                    colour[1] as u8,
+                    colour[2] as u8,
+                    colour[3] as u8,
+                ];
+            } else if value.ends_with('%') {
+                let p = value.trim_end_matches('%').parse::<f64>().map_err(|_| bad_line())?;
+                stops.push((p / 100f64, true, colour));
+            } else {
+                let v = value.parse::<f64>().map_err(|_| bad_line())?;
+                stops.push((v, false, colour));
+            }
+        }
+        if stops.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("The palette file {} does not contain any colours.", file_name),
+            ));
+        }
+        Ok(ColourRamp { stops, nodata })
+    }
+
+    /// Reverses the order of the ramp's colours, keeping their positions.
+    pub(super) fn reverse(&mut self) {
+        let colours: Vec<[f64; 4]> = self.stops.iter().rev().map(|s| s.2).collect();
+        for (stop, colour) in self.stops.iter_mut().zip(colours) {
+            stop.2 = colour;
+        }
+    }
+
+    /// Returns whether any of the ramp's colours are positioned relative to the range of values.
+    pub(super) fn is_relative(&self) -> bool {
+        self.stops.iter().any(|s| s.1)
+    }
+
+    /// The colour of NoData cells.
+    pub(super) fn nodata_colour(&self) -> Rgba {
+        self.nodata
+    }
+
+    /// Returns the ramp's stops as values, for the range of values from `min` to `max`, in order.
+    pub(super) fn resolve(&self, min: f64, max: f64) -> Vec<(f64, [f64; 4])> {
+        let mut stops: Vec<(f64, [f64; 4])> = self
+            .stops
+            .iter()
+            .map(|&(p, relative, c)| (if relative { min + p * (max - min) } else { p }, c))
+            .collect();
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        stops
+    }
+}
+
+/// Returns the colour of a value from the resolved stops of a colour ramp. Values beyond the
+/// ends of the ramp are given the colours of its ends.
+pub(super) fn ramp_colour(stops: &[(f64, [f64; 4])], z: f64) -> [f64; 4] {
+    if z <= stops[0].0 {
+        return stops[0].1;
+    }
+    for i in 1..stops.len() {
+        if z <= stops[i].0 {
+            let (z0, c0) = stops[i - 1];
+            let (z1, c1) = stops[i];
+            let t = if z1 > z0 { (z - z0) / (z1 - z0) } else { 1f64 };
+            let mut c = [0f64; 4];
+            for k in 0..4 {
+                c[k] = c0[k] + t * (c1[k] - c0[k]);
+            }
+            return c;
+        }
+    }
+    stops[stops.len() - 1].1
+}
+
+/// Returns the range of values of a raster that is stretched over the full range of intensities,
+/// using one of the stretches 'none' (the values are intensities, i.e. 0-255), 'minmax' (the
+/// minimum and maximum values), 'percent' (the values after clipping `clip` percent of the cells
+/// from each tail of the distribution), and 'stdev' (`num_stdev` standard deviations either side
+/// of the mean).
+pub(super) fn stretch_range(raster: &Raster, stretch: &str, clip: f64, num_stdev: f64) -> (f64, f64) {
+    match stretch {
+        "none" => (0f64, 255f64),
+        "percent" => raster.calculate_clip_values(clip),
+        "stdev" => {
+            let (mean, stdev) = raster.calculate_mean_and_stdev();
+            (mean - num_stdev * stdev, mean + num_stdev * stdev)
+        }
+        _ => raster.calculate_clip_values(0f64),
+    }
+}
+
+/// Scales a value in the range from `low` to `high` to an 8-bit intensity, applying a gamma
+/// correction; gamma values greater than one brighten the mid-tones.
+pub(super) fn scale_intensity(z: f64, low: f64, high: f64, gamma: f64) -> u8 {
+    let t = if high > low {
+        ((z - low) / (high - low)).max(0f64).min(1f64)
+    } else {
+        0.5f64
+    };
+    (255f64 * t.powf(1f64 / gamma)).round() as u8
+}
+
+/// Writes an image with the grid of `base` to a PNG file, if the file name has the extension
+/// .png, or otherwise to an RGBA32 raster. The pixels of the image are in row-major order. PNG
+/// images are georeferenced by a world file (.pgw) and, if the base raster's coordinate
+/// reference system is known, a .prj file.
+pub(super) fn write_image(
+    file_name: &str,
+    base: &Raster,
+    pixels: &[Rgba],
+    metadata: Vec<String>,
+) -> Result<(), Error> {
+    let rows = base.configs.rows;
+    let columns = base.configs.columns;
+    if file_name.to_lowercase().ends_with(".png") {
+        write_png(file_name, columns, rows, pixels)?;
+        let path = Path::new(file_name);
+        let mut world_file = BufWriter::new(File::create(path.with_extension("pgw"))?);
+        writeln!(world_file, "{}", base.configs.resolution_x)?;
+        writeln!(world_file, "0.0")?;
+        writeln!(world_file, "0.0")?;
+        writeln!(world_file, "{}", -base.configs.resolution_y)?;
+        writeln!(world_file, "{}", base.configs.west + base.configs.resolution_x / 2f64)?;
+        writeln!(world_file, "{}", base.configs.north - base.configs.resolution_y / 2f64)?;
+        world_file.flush()?;
+        let wkt = base.configs.coordinate_ref_system_wkt.trim();
+        if !wkt.is_empty() && wkt != "Unknown EPSG Code" {
+            fs::write(path.with_extension("prj"), wkt)?;
+        }
+        return Ok(());
+    }
+
+    let mut output = Raster::initialize_using_file(file_name, base);
+    output.configs.photometric_interp = PhotometricInterpretation::RGB;
+    output.configs.data_type = DataType::RGBA32;
+    output.configs.nodata = 0f64;
+    for row in 0..rows {
+        let data = pixels[row * columns..(row + 1) * columns]
+            .iter()
+            .map(|p| {
+                (((p[3] as u32) << 24) | ((p[2] as u32) << 16) | ((p[1] as u32) << 8) | p[0] as u32)
+                    as f64
+            })
+            .collect();
+        output.set_row_data(row as isize, data);
+    }
+    for entry in metadata {
+        output.add_metadata_entry(entry);
+    }
+    output.write()
+}
+
+/// Writes an 8-bit RGBA PNG image.
+fn write_png(file_name: &str, width: usize, height: usize, pixels: &[Rgba]) -> Result<(), Error> {
+    let mut writer = BufWriter::new(File::create(file_name)?);
+    writer.write_all(&encode_png(width, height, pixels))?;
+    writer.flush()
+}
+
+/// Encodes an image, with its pixels in row-major order, as an 8-bit RGBA PNG.
+pub(super) fn encode_png(width: usize, height: usize, pixels: &[Rgba]) -> Vec<u8> {
+    // Each row of the image is preceded by its filter type, zero for none.
+    let mut raw = Vec::with_capacity(height * (width * 4 + 1));
+    for row in 0..height {
+        raw.push(0u8);
+        for p in &pixels[row * width..(row + 1) * width] {
+            raw.extend_from_slice(p);
+        }
+    }
+    // bit depth 8, colour type 6 (RGBA)
+    png::encode_png(width, height, 8, 6, &raw)
+}
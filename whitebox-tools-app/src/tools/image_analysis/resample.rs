@@ -8,14 +8,14 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
+use whitebox_common::errors::ErrorCause;
 
 /// This tool can be used to modify the grid resolution of one or more rasters. The user
 /// specifies the names of one or more input rasters (`--inputs`) and the output raster
@@ -111,7 +111,7 @@ impl Resample {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{} -r={} -v --wd='*path*to*data*' -i='image1.tif;image2.tif;image3.tif' --destination=dest.tif --method='cc", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{} -r={} -v --wd='*path*to*data*' -i='image1.tif;image2.tif;image3.tif' -o=output.tif --base=dest.tif --method='cc'", short_exe, name).replace("*", &sep);
 
         Resample {
             name: name,
@@ -156,87 +156,44 @@ impl WhiteboxTool for Resample {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut base_file = String::new();
         let mut cell_size = 0f64;
         let mut cell_size_specified = false;
         let mut base_file_specified = false;
         let mut method = String::from("cc");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_f64("cell_size") {
+            cell_size = value;
+            if cell_size > 0f64 {
+                cell_size_specified = true;
+            } else {
+                panic!(
+                    "Error, when specified, the cell_size parameter must be larger than 0.0."
+                );
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-cell_size" {
-                cell_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if cell_size > 0f64 {
-                    cell_size_specified = true;
-                } else {
-                    panic!(
-                        "Error, when specified, the cell_size parameter must be larger than 0.0."
-                    );
-                }
-            } else if flag_val == "-base" {
-                base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                base_file_specified = true;
-            } else if flag_val == "-method" {
-                method = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                if method.to_lowercase().contains("nn") || method.to_lowercase().contains("nearest")
-                {
-                    method = "nn".to_string();
-                } else if method.to_lowercase().contains("bilinear")
-                    || method.to_lowercase().contains("bi")
-                {
-                    method = "bilinear".to_string();
-                } else if method.to_lowercase().contains("cc")
-                    || method.to_lowercase().contains("cubic")
-                {
-                    method = "cc".to_string();
-                }
+        }
+        if let Some(value) = parsed.get_string("base") {
+            base_file = value;
+            base_file_specified = true;
+        }
+        if let Some(value) = parsed.get_string("method") {
+            method = value;
+            if method.to_lowercase().contains("nn") || method.to_lowercase().contains("nearest")
+            {
+                method = "nn".to_string();
+            } else if method.to_lowercase().contains("bilinear")
+                || method.to_lowercase().contains("bi")
+            {
+                method = "bilinear".to_string();
+            } else if method.to_lowercase().contains("cc")
+                || method.to_lowercase().contains("cubic")
+            {
+                method = "cc".to_string();
             }
         }
 
@@ -251,19 +208,14 @@ impl WhiteboxTool for Resample {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         // see if the destination file exists.
         if base_file_specified && !path::Path::new(&base_file).exists() {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
+            return Err(ErrorCause::FileNotFound.error(
                 "The base raster file (--base) does not exist.",
             ));
         }
@@ -302,9 +254,7 @@ impl WhiteboxTool for Resample {
             let value = input_vec[i];
             if !value.trim().is_empty() {
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 inputs.push(
                     Raster::new(&input_file, "r")
                         .expect(&format!("Error reading image file {}", input_file)),
@@ -334,9 +284,7 @@ impl WhiteboxTool for Resample {
         // If both are specified, the base raster takes priority.
 
         let mut output = if base_file_specified || cell_size <= 0f64 {
-            if !base_file.contains(&sep) && !base_file.contains("/") {
-                base_file = format!("{}{}", working_directory, base_file);
-            }
+            base_file = resolve_input_path(&base_file, working_directory)?;
             let base = Raster::new(&base_file, "r")?;
             Raster::initialize_using_file(&output_file, &base)
         } else {
@@ -363,7 +311,7 @@ impl WhiteboxTool for Resample {
             configs.nodata = nodata_vals[0];
             configs.data_type = inputs[0].configs.data_type;
             configs.photometric_interp = PhotometricInterpretation::Continuous;
-            configs.projection = inputs[0].configs.projection.clone();
+            configs.set_crs(&inputs[0].configs.crs());
 
             Raster::initialize_using_config(&output_file, &configs)
         };
@@ -387,12 +335,8 @@ impl WhiteboxTool for Resample {
         let y = Arc::new(y);
         let inputs = Arc::new(inputs);
         let nodata_vals = Arc::new(nodata_vals);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         if method == "nn" {
             for tid in 0..num_procs {
@@ -401,7 +345,7 @@ impl WhiteboxTool for Resample {
                 let x = x.clone();
                 let y = y.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z: f64;
                     let (mut col_src, mut row_src): (isize, isize);
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -413,27 +357,30 @@ impl WhiteboxTool for Resample {
                                 // row_src = ((inputs[i].configs.north - y[row as usize]) / inputs[i].configs.resolution_y).round() as isize;
                                 // col_src = ((x[col as usize] - inputs[i].configs.west) / inputs[i].configs.resolution_x).round() as isize;
                                 z = inputs[i].get_value(row_src, col_src);
-                                if z != nodata_vals[i] {
+                                if !is_nodata(z, nodata_vals[i]) {
                                     data[col as usize] = z;
                                     break;
                                 }
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
-                    if data[col as usize] != nodata {
+                    if !is_nodata(data[col as usize], nodata) {
                         output.set_value(row, col, data[col as usize]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -448,7 +395,7 @@ impl WhiteboxTool for Resample {
                 let x = x.clone();
                 let y = y.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z: f64;
                     let shift_x = [-1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2, -1, 0, 1, 2];
                     let shift_y = [-1, -1, -1, -1, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2];
@@ -483,10 +430,10 @@ impl WhiteboxTool for Resample {
                                     dy = row_n as f64 - row_src;
                                     dx = col_n as f64 - col_src;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
+                                    if (dx + dy) != 0f64 && !is_nodata(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
+                                    } else if is_nodata(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         data[col as usize] = neighbour[n][0];
@@ -504,21 +451,24 @@ impl WhiteboxTool for Resample {
                                 }
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns as usize {
-                    if data[col] != nodata {
+                    if !is_nodata(data[col], nodata) {
                         output.set_value(row, col as isize, data[col]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -533,7 +483,7 @@ impl WhiteboxTool for Resample {
                 let x = x.clone();
                 let y = y.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z: f64;
                     let shift_x = [0, 1, 0, 1];
                     let shift_y = [0, 0, 1, 1];
@@ -566,10 +516,10 @@ impl WhiteboxTool for Resample {
                                     dy = row_n as f64 - row_src;
                                     dx = col_n as f64 - col_src;
 
-                                    if (dx + dy) != 0f64 && neighbour[n][0] != nodata_vals[i] {
+                                    if (dx + dy) != 0f64 && !is_nodata(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 1f64 / (dx * dx + dy * dy);
                                         sum_dist += neighbour[n][1];
-                                    } else if neighbour[n][0] == nodata_vals[i] {
+                                    } else if is_nodata(neighbour[n][0], nodata_vals[i]) {
                                         neighbour[n][1] = 0f64;
                                     } else {
                                         data[col as usize] = neighbour[n][0];
@@ -587,21 +537,24 @@ impl WhiteboxTool for Resample {
                                 }
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns as usize {
-                    if data[col] != nodata {
+                    if !is_nodata(data[col], nodata) {
                         output.set_value(row, col as isize, data[col]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -8,15 +8,14 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool transforms three raster images of multispectral data (red, green, and blue channels) into their equivalent
 /// intensity, hue, and saturation (IHS; sometimes HSI or HIS) images. Intensity refers to the brightness of a color, hue
@@ -78,7 +77,7 @@ impl RgbToIhs {
 
         parameters.push(ToolParameter{
             name: "Input Green Band File (optional; only if colour-composite not specified)".to_owned(), 
-            flags: vec!["--green".to_owned()], 
+            flags: vec!["--g".to_owned(), "--green".to_owned()], 
             description: "Input green band image file. Optionally specified if colour-composite not specified.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -87,7 +86,7 @@ impl RgbToIhs {
 
         parameters.push(ToolParameter{
             name: "Input Blue Band File (optional; only if colour-composite not specified)".to_owned(), 
-            flags: vec!["--blue".to_owned()], 
+            flags: vec!["--b".to_owned(), "--blue".to_owned()], 
             description: "Input blue band image file. Optionally specified if colour-composite not specified.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -105,7 +104,7 @@ impl RgbToIhs {
 
         parameters.push(ToolParameter {
             name: "Output Intensity File".to_owned(),
-            flags: vec!["--intensity".to_owned()],
+            flags: vec!["--i".to_owned(), "--intensity".to_owned()],
             description: "Output intensity raster file.".to_owned(),
             parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
             default_value: None,
@@ -114,7 +113,7 @@ impl RgbToIhs {
 
         parameters.push(ToolParameter {
             name: "Output Hue File".to_owned(),
-            flags: vec!["--hue".to_owned()],
+            flags: vec!["--h".to_owned(), "--hue".to_owned()],
             description: "Output hue raster file.".to_owned(),
             parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
             default_value: None,
@@ -123,7 +122,7 @@ impl RgbToIhs {
 
         parameters.push(ToolParameter {
             name: "Output Saturation File".to_owned(),
-            flags: vec!["--saturation".to_owned()],
+            flags: vec!["--s".to_owned(), "--saturation".to_owned()],
             description: "Output saturation raster file.".to_owned(),
             parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
             default_value: None,
@@ -196,91 +195,20 @@ impl WhiteboxTool for RgbToIhs {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut red_file = String::new();
-        let mut green_file = String::new();
-        let mut blue_file = String::new();
-        let mut intensity_file = String::new();
-        let mut hue_file = String::new();
-        let mut saturation_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut red_file = parsed.get_string("red").unwrap_or_default();
+        let mut green_file = parsed.get_string("green").unwrap_or_default();
+        let mut blue_file = parsed.get_string("blue").unwrap_or_default();
+        let mut intensity_file = parsed.get_string("intensity").unwrap_or_default();
+        let mut hue_file = parsed.get_string("hue").unwrap_or_default();
+        let mut saturation_file = parsed.get_string("saturation").unwrap_or_default();
         let mut composite_file = String::new();
         let mut use_composite = false;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-red" || vec[0].to_lowercase() == "--red" {
-                if keyval {
-                    red_file = vec[1].to_string();
-                } else {
-                    red_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-g"
-                || vec[0].to_lowercase() == "-green"
-                || vec[0].to_lowercase() == "--green"
-            {
-                if keyval {
-                    green_file = vec[1].to_string();
-                } else {
-                    green_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-b"
-                || vec[0].to_lowercase() == "-blue"
-                || vec[0].to_lowercase() == "--blue"
-            {
-                if keyval {
-                    blue_file = vec[1].to_string();
-                } else {
-                    blue_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-composite"
-                || vec[0].to_lowercase() == "--composite"
-            {
-                if keyval {
-                    composite_file = vec[1].to_string();
-                } else {
-                    composite_file = args[i + 1].to_string();
-                }
-                use_composite = true;
-            } else if vec[0].to_lowercase() == "-i"
-                || vec[0].to_lowercase() == "-intensity"
-                || vec[0].to_lowercase() == "--intensity"
-            {
-                if keyval {
-                    intensity_file = vec[1].to_string();
-                } else {
-                    intensity_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-h"
-                || vec[0].to_lowercase() == "-hue"
-                || vec[0].to_lowercase() == "--hue"
-            {
-                if keyval {
-                    hue_file = vec[1].to_string();
-                } else {
-                    hue_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-s"
-                || vec[0].to_lowercase() == "-saturation"
-                || vec[0].to_lowercase() == "--saturation"
-            {
-                if keyval {
-                    saturation_file = vec[1].to_string();
-                } else {
-                    saturation_file = args[i + 1].to_string();
-                }
-            }
+        if let Some(value) = parsed.get_string("composite") {
+            composite_file = value;
+            use_composite = true;
         }
 
         if verbose {
@@ -294,39 +222,19 @@ impl WhiteboxTool for RgbToIhs {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !red_file.contains(&sep) && !red_file.contains("/") {
-            red_file = format!("{}{}", working_directory, red_file);
-        }
-        if !green_file.contains(&sep) && !green_file.contains("/") {
-            green_file = format!("{}{}", working_directory, green_file);
-        }
-        if !blue_file.contains(&sep) && !blue_file.contains("/") {
-            blue_file = format!("{}{}", working_directory, blue_file);
-        }
-        if !composite_file.contains(&sep) && !composite_file.contains("/") {
-            composite_file = format!("{}{}", working_directory, composite_file);
-        }
-        if !intensity_file.contains(&sep) && !intensity_file.contains("/") {
-            intensity_file = format!("{}{}", working_directory, intensity_file);
-        }
-        if !hue_file.contains(&sep) && !hue_file.contains("/") {
-            hue_file = format!("{}{}", working_directory, hue_file);
-        }
-        if !saturation_file.contains(&sep) && !saturation_file.contains("/") {
-            saturation_file = format!("{}{}", working_directory, saturation_file);
-        }
+        red_file = resolve_input_path(&red_file, working_directory)?;
+        green_file = resolve_input_path(&green_file, working_directory)?;
+        blue_file = resolve_input_path(&blue_file, working_directory)?;
+        composite_file = resolve_input_path(&composite_file, working_directory)?;
+        intensity_file = resolve_path(&intensity_file, working_directory);
+        hue_file = resolve_path(&hue_file, working_directory);
+        saturation_file = resolve_path(&saturation_file, working_directory);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         if !use_composite {
             if verbose {
@@ -363,15 +271,15 @@ impl WhiteboxTool for RgbToIhs {
             if input_r.configs.rows != input_g.configs.rows
                 || input_r.configs.columns != input_g.configs.columns
             {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                    "The input files must have the same number of rows and columns and spatial extent."));
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
             }
+            warn_if_crs_differs(&input_r, &input_g, verbose);
             if input_r.configs.rows != input_b.configs.rows
                 || input_r.configs.columns != input_b.configs.columns
             {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                    "The input files must have the same number of rows and columns and spatial extent."));
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
             }
+            warn_if_crs_differs(&input_r, &input_b, verbose);
 
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
@@ -379,7 +287,7 @@ impl WhiteboxTool for RgbToIhs {
                 let input_g = input_g.clone();
                 let input_b = input_b.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     // let (mut r, mut g, mut b): (u32, u32, u32);
                     let (mut red, mut green, mut blue): (f64, f64, f64);
                     // let (mut i, mut h, mut s, mut m): (f64, f64, f64, f64);
@@ -395,7 +303,7 @@ impl WhiteboxTool for RgbToIhs {
                             red = input_r[(row, col)];
                             green = input_g[(row, col)];
                             blue = input_b[(row, col)];
-                            if red != nodata_r && green != nodata_g && blue != nodata_b {
+                            if !is_nodata(red, nodata_r) && !is_nodata(green, nodata_g) && !is_nodata(blue, nodata_b) {
                                 // r = ((red - red_min) / (red_max - red_min) * 255f64) as u32;
                                 // if r > 255u32 {
                                 //     r = 255u32;
@@ -421,8 +329,9 @@ impl WhiteboxTool for RgbToIhs {
                                 intensity_data[col as usize] = i;
                             }
                         }
-                        tx.send((row, intensity_data, hue_data, saturation_data))
-                            .unwrap();
+                        if tx.send((row, intensity_data, hue_data, saturation_data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -444,10 +353,11 @@ impl WhiteboxTool for RgbToIhs {
                 output_i.set_row_data(data.0, data.1);
                 output_h.set_row_data(data.0, data.2);
                 output_s.set_row_data(data.0, data.3);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -533,18 +443,23 @@ impl WhiteboxTool for RgbToIhs {
 
             // find the overall minimum and range
             let (tx, rx) = mpsc::channel();
+            let token = reporter.cancellation_token();
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                let token = token.clone();
+                spawn(move || {
                     let mut overall_min = f64::INFINITY;
                     let mut overall_max = f64::NEG_INFINITY;
                     let (mut r, mut g, mut b): (f64, f64, f64);
                     let mut z: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        if token.is_cancelled() {
+                            break;
+                        }
                         for col in 0..columns {
                             z = input[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 r = (z as u32 & 0xFF) as f64;
                                 g = ((z as u32 >> 8) & 0xFF) as f64;
                                 b = ((z as u32 >> 16) & 0xFF) as f64;
@@ -572,7 +487,9 @@ impl WhiteboxTool for RgbToIhs {
                             }
                         }
                     }
-                    tx.send((overall_min, overall_max)).unwrap();
+                    if tx.send((overall_min, overall_max)).is_err() {
+                        return;
+                    }
                 });
             }
 
@@ -586,10 +503,11 @@ impl WhiteboxTool for RgbToIhs {
                 if data.1 > overall_max {
                     overall_max = data.1;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * tid as f64 / (num_procs - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -601,7 +519,7 @@ impl WhiteboxTool for RgbToIhs {
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     // let (mut r, mut g, mut b): (f64, f64, f64);
                     // let (mut i, mut h, mut s, mut m): (f64, f64, f64, f64);
                     // let (mut i, mut h, mut s): (f64, f64, f64);
@@ -612,7 +530,7 @@ impl WhiteboxTool for RgbToIhs {
                         let mut saturation_data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 // r = (z as u32 & 0xFF) as f64;
                                 // g = ((z as u32 >> 8) & 0xFF) as f64;
                                 // b = ((z as u32 >> 16) & 0xFF) as f64;
@@ -672,8 +590,9 @@ impl WhiteboxTool for RgbToIhs {
                                 saturation_data[col as usize] = s;
                             }
                         }
-                        tx.send((row, intensity_data, hue_data, saturation_data))
-                            .unwrap();
+                        if tx.send((row, intensity_data, hue_data, saturation_data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -695,10 +614,11 @@ impl WhiteboxTool for RgbToIhs {
                 output_i.set_row_data(data.0, data.1);
                 output_h.set_row_data(data.0, data.2);
                 output_s.set_row_data(data.0, data.3);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -0,0 +1,262 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool converts synthetic aperture radar (SAR) backscatter (`--input`) between a linear scale and
+/// decibels (dB). With `--direction=to_db` (the default), linear intensity (power) values are converted to
+/// 10 log10(*x*), and with `--direction=to_linear`, decibel values are converted back to 10^(*x*/10). If the
+/// `--amplitude` flag is specified, the linear values are amplitudes rather than intensities, and the factor
+/// is 20 rather than 10. Linear values that are zero or negative have no decibel equivalent and are output
+/// as NoData.
+///
+/// Speckle filters, such as those of the `SarSpeckleFilter` tool, model speckle as multiplicative noise
+/// and should be applied to linear intensities, while decibels are better suited to display and to
+/// classification.
+///
+/// # See Also
+/// `SarSpeckleFilter`, `Log10`
+pub struct SarDecibelConversion {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SarDecibelConversion {
+    pub fn new() -> SarDecibelConversion {
+        // public constructor
+        let name = "SarDecibelConversion".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Converts SAR backscatter between linear intensity or amplitude and decibels.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input SAR backscatter raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Direction".to_owned(),
+            flags: vec!["--direction".to_owned()],
+            description: "Direction of the conversion; either 'to_db' or 'to_linear'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "to_db".to_owned(),
+                "to_linear".to_owned(),
+            ]),
+            default_value: Some("to_db".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Linear values are amplitudes?".to_owned(),
+            flags: vec!["--amplitude".to_owned()],
+            description: "Optional flag indicating that the linear values are amplitudes rather than intensities.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=sigma0_vv.tif -o=sigma0_vv_db.tif --direction=to_db",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SarDecibelConversion {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SarDecibelConversion {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut direction = String::from("to_db");
+        let amplitude = parsed.get_bool("amplitude");
+
+        if let Some(value) = parsed.get_string("direction") {
+            direction = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let to_db = if direction.contains("lin") {
+            false
+        } else if direction.contains("db") {
+            true
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --direction parameter must be either 'to_db' or 'to_linear'.",
+            ));
+        };
+        let factor = if amplitude { 20f64 } else { 10f64 };
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let nodata = input.configs.nodata;
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        if output.configs.data_type != DataType::F32 && output.configs.data_type != DataType::F64 {
+            output.configs.data_type = DataType::F32;
+        }
+        for row in 0..rows {
+            let mut data = input.get_row_data(row);
+            for z in data.iter_mut() {
+                if is_nodata(*z, nodata) {
+                    continue;
+                }
+                *z = if to_db {
+                    if *z > 0f64 {
+                        factor * z.log10()
+                    } else {
+                        nodata
+                    }
+                } else {
+                    10f64.powf(*z / factor)
+                };
+            }
+            output.set_row_data(row, data);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!(
+            "Conversion: {}",
+            if to_db { "linear to dB" } else { "dB to linear" }
+        ));
+        output.add_metadata_entry(format!("Amplitude: {}", amplitude));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
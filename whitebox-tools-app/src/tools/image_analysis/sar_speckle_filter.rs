@@ -0,0 +1,430 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool reduces the speckle in synthetic aperture radar (SAR) backscatter imagery (`--input`).
+/// Speckle is multiplicative noise, with a standard deviation proportional to the backscatter, and so
+/// general-purpose smoothing filters, such as `MeanFilter`, `GaussianFilter`, and `LeeSigmaFilter`,
+/// either leave it in bright areas or over-smooth dark areas and blur edges. The adaptive filters in this
+/// tool instead compare the coefficient of variation of the backscatter within the filter window
+/// (`--filter`), *Ci* = *s* / *m*, with that expected of pure speckle, *Cu* = 1 / sqrt(*L*), where *L* is the
+/// equivalent number of looks of the image (`--looks`). Homogeneous areas, where *Ci* is close to *Cu*, are
+/// smoothed strongly, while edges and point targets, where *Ci* is larger, are preserved. The `--method`
+/// may be:
+///
+/// - `lee`: the Lee (1980) filter, *R* = *m* + *W*(*I* - *m*), with *W* = 1 - *Cu*²/*Ci*².
+/// - `enhanced lee`: the enhanced Lee filter of Lopes et al. (1990), which returns the mean where *Ci* <= *Cu*,
+///   the original value where *Ci* >= *Cmax* = sqrt(1 + 2/*L*), and otherwise the weighted combination
+///   with *W* = exp(-*K*(*Ci* - *Cu*)/(*Cmax* - *Ci*)).
+/// - `frost`: the Frost et al. (1982) filter, a weighted mean of the window with weights that decay
+///   exponentially with distance from the centre, at a rate of *K* *Ci*² per cell.
+/// - `gamma map`: the Gamma maximum a posteriori filter of Lopes et al. (1990), which assumes that both
+///   the scene reflectivity and the speckle are Gamma-distributed, with the same thresholds as the
+///   enhanced Lee filter.
+///
+/// The damping factor *K* (`--damping`) is used by the enhanced Lee and Frost filters; larger values
+/// preserve more of the texture near edges. The default is 1.
+///
+/// The filters assume that the input is backscatter intensity (power) on a linear scale. Images in
+/// decibels can be filtered by specifying the `--db` flag, in which case the values are converted to
+/// linear intensity before filtering and the output is converted back to decibels; alternatively, use
+/// the `SarDecibelConversion` tool. NoData cells are ignored in the filter windows.
+///
+/// # References
+/// Frost, V. S., Stiles, J. A., Shanmugan, K. S., & Holtzman, J. C. (1982). A model for radar images and
+/// its application to adaptive digital filtering of multiplicative noise. *IEEE Transactions on Pattern
+/// Analysis and Machine Intelligence*, 4(2), 157-166.
+///
+/// Lee, J. S. (1980). Digital image enhancement and noise filtering by use of local statistics. *IEEE
+/// Transactions on Pattern Analysis and Machine Intelligence*, 2(2), 165-168.
+///
+/// Lopes, A., Touzi, R., & Nezry, E. (1990). Adaptive speckle filters and scene heterogeneity. *IEEE
+/// Transactions on Geoscience and Remote Sensing*, 28(6), 992-1000.
+///
+/// # See Also
+/// `SarDecibelConversion`, `LeeSigmaFilter`, `MeanFilter`
+pub struct SarSpeckleFilter {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SarSpeckleFilter {
+    pub fn new() -> SarSpeckleFilter {
+        // public constructor
+        let name = "SarSpeckleFilter".to_string();
+        let toolbox = "Image Processing Tools/Filters".to_string();
+        let description =
+            "Reduces speckle in SAR backscatter imagery using the Lee, enhanced Lee, Frost, or Gamma-MAP filters.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input SAR backscatter raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Speckle filter; one of 'lee', 'enhanced lee', 'frost', and 'gamma map'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "lee".to_owned(),
+                "enhanced lee".to_owned(),
+                "frost".to_owned(),
+                "gamma map".to_owned(),
+            ]),
+            default_value: Some("enhanced lee".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Filter Size".to_owned(),
+            flags: vec!["--filter".to_owned()],
+            description: "Size of the square filter window, in cells (odd).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("7".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Equivalent Number of Looks".to_owned(),
+            flags: vec!["--looks".to_owned()],
+            description: "Equivalent number of looks (ENL) of the image.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Damping Factor".to_owned(),
+            flags: vec!["--damping".to_owned()],
+            description: "Damping factor of the enhanced Lee and Frost filters.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input in decibels?".to_owned(),
+            flags: vec!["--db".to_owned()],
+            description: "Optional flag indicating that the input backscatter is in decibels.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=sigma0_vv.tif -o=filtered.tif --method=\"gamma map\" --filter=7 --looks=4.4",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SarSpeckleFilter {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SarSpeckleFilter {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut method = String::from("enhanced lee");
+        let mut filter_size = parsed.get_i64("filter").map(|v| v as isize).unwrap_or(7isize);
+        let looks = parsed.get_f64("looks").unwrap_or(1f64);
+        let damping = parsed.get_f64("damping").unwrap_or(1f64);
+        let in_db = parsed.get_bool("db");
+
+        if let Some(value) = parsed.get_string("method") {
+            method = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        // 0 = Lee, 1 = enhanced Lee, 2 = Frost, 3 = Gamma-MAP
+        let filter_type = if method.contains("enh") {
+            1
+        } else if method.contains("lee") {
+            0
+        } else if method.contains("frost") {
+            2
+        } else if method.contains("gamma") || method.contains("map") {
+            3
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --method parameter must be one of 'lee', 'enhanced lee', 'frost', or 'gamma map'.",
+            ));
+        };
+        if looks <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The equivalent number of looks (--looks) must be greater than zero.",
+            ));
+        }
+        if filter_size < 3 {
+            filter_size = 3;
+        }
+        if filter_size % 2 == 0 {
+            filter_size += 1;
+        }
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let half = filter_size / 2;
+        let cu = 1f64 / looks.sqrt();
+        let cmax = (1f64 + 2f64 / looks).sqrt();
+
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let linear = |z: f64| {
+                    if in_db {
+                        10f64.powf(z / 10f64)
+                    } else {
+                        z
+                    }
+                };
+                let mut window: Vec<(f64, f64)> = Vec::with_capacity((filter_size * filter_size) as usize);
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        let z = input.get_value(row, col);
+                        if is_nodata(z, nodata) {
+                            continue;
+                        }
+                        let z = linear(z);
+                        // the values in the window and their distances from the centre
+                        window.clear();
+                        for r in (row - half)..=(row + half) {
+                            for c in (col - half)..=(col + half) {
+                                let zn = input.get_value(r, c);
+                                if !is_nodata(zn, nodata) {
+                                    let dist = (((r - row) * (r - row) + (c - col) * (c - col)) as f64).sqrt();
+                                    window.push((linear(zn), dist));
+                                }
+                            }
+                        }
+                        let n = window.len() as f64;
+                        let mean = window.iter().map(|w| w.0).sum::<f64>() / n;
+                        let variance = (window.iter().map(|w| w.0 * w.0).sum::<f64>() / n - mean * mean).max(0f64);
+                        let ci = if mean > 0f64 { variance.sqrt() / mean } else { 0f64 };
+                        let filtered = match filter_type {
+                            0 => {
+                                let w = if ci > 0f64 {
+                                    (1f64 - (cu * cu) / (ci * ci)).max(0f64)
+                                } else {
+                                    0f64
+                                };
+                                mean + w * (z - mean)
+                            }
+                            1 => {
+                                if ci <= cu {
+                                    mean
+                                } else if ci >= cmax {
+                                    z
+                                } else {
+                                    let w = (-damping * (ci - cu) / (cmax - ci)).exp();
+                                    mean * w + z * (1f64 - w)
+                                }
+                            }
+                            2 => {
+                                let a = damping * ci * ci;
+                                let (mut sum, mut weights) = (0f64, 0f64);
+                                for &(v, dist) in &window {
+                                    let w = (-a * dist).exp();
+                                    sum += w * v;
+                                    weights += w;
+                                }
+                                sum / weights
+                            }
+                            _ => {
+                                if ci <= cu {
+                                    mean
+                                } else if ci >= cmax {
+                                    z
+                                } else {
+                                    let alpha = (1f64 + cu * cu) / (ci * ci - cu * cu);
+                                    let b = alpha - looks - 1f64;
+                                    let d = mean * mean * b * b + 4f64 * alpha * looks * z * mean;
+                                    (b * mean + d.max(0f64).sqrt()) / (2f64 * alpha)
+                                }
+                            }
+                        };
+                        data[col as usize] = if in_db {
+                            if filtered > 0f64 {
+                                10f64 * filtered.log10()
+                            } else {
+                                nodata
+                            }
+                        } else {
+                            filtered
+                        };
+                    }
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        if output.configs.data_type != DataType::F32 && output.configs.data_type != DataType::F64 {
+            output.configs.data_type = DataType::F32;
+        }
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Method: {}", method));
+        output.add_metadata_entry(format!("Filter size: {}", filter_size));
+        output.add_metadata_entry(format!("Equivalent number of looks: {}", looks));
+        if filter_type == 1 || filter_type == 2 {
+            output.add_metadata_entry(format!("Damping factor: {}", damping));
+        }
+        output.add_metadata_entry(format!("Input in decibels: {}", in_db));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
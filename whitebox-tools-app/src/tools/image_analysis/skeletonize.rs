@@ -0,0 +1,295 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{distance_to_background, zhang_suen_thinning};
+use whitebox_common::structures::Array2D;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+
+/// This tool reduces the features in a binary raster (`--input`), such as a classified water or
+/// road mask, to their one-cell wide, 8-connected skeletons (`--output`), e.g. to derive river and
+/// road centrelines. All positive cells are foreground and all other cells, including NoData cells,
+/// are background. The skeleton is found using the thinning algorithm of Zhang and Suen (1984),
+/// which, unlike `LineThinning`, removes the cells of each pass in parallel, giving skeletons that
+/// are centred within elongated features.
+///
+/// Optionally, the tool also creates a feature width raster (`--width`). Each skeleton cell is
+/// assigned the width of the feature at that point, i.e. twice the distance from the cell to the
+/// feature's edge, in map units, and all other cells are NoData. The distance to the edge is derived
+/// from the exact Euclidean distance transform (Felzenszwalb and Huttenlocher, 2012) of the
+/// background, less half a cell, because the transform measures distance to the centre of the
+/// nearest background cell. A feature one cell wide therefore has a width of one cell. Skeleton
+/// cells of features that have no background cells within the raster have NoData widths.
+///
+/// Short spurs, which the thinning produces at irregularities along the edges of features, can be
+/// removed from the input beforehand using the `MorphologicalFilter` (`--operation=opening`) or from
+/// the skeleton using `RemoveSpurs`.
+///
+/// # References
+/// Felzenszwalb, P. F., & Huttenlocher, D. P. (2012). Distance transforms of sampled functions.
+/// *Theory of Computing*, 8, 415-428.
+///
+/// Zhang, T. Y., & Suen, C. Y. (1984). A fast parallel algorithm for thinning digital patterns.
+/// *Communications of the ACM*, 27(3), 236-239.
+///
+/// # See Also
+/// `LineThinning`, `RemoveSpurs`, `EuclideanDistance`, `MorphologicalFilter`
+pub struct Skeletonize {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl Skeletonize {
+    pub fn new() -> Skeletonize {
+        // public constructor
+        let name = "Skeletonize".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Thins the features of a binary raster to their skeletons and optionally maps their widths.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input binary raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Skeleton File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output skeleton raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Width File (optional)".to_owned(),
+            flags: vec!["--width".to_owned()],
+            description: "Optional output raster file of the feature widths at the skeleton cells.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=water.tif -o=centreline.tif --width=river_width.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        Skeletonize {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for Skeletonize {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut width_file = parsed.get_string("width").unwrap_or_default();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        if !width_file.is_empty() {
+            width_file = resolve_path(&width_file, working_directory);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let mut image: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if !is_nodata(z, nodata) && z > 0f64 {
+                    image.set_value(row, col, 1u8);
+                }
+            }
+        }
+
+        // the distances must be measured before the features are thinned
+        let distances = if !width_file.is_empty() {
+            if verbose {
+                println!("Calculating distances...");
+            }
+            Some(distance_to_background(
+                &image,
+                input.configs.resolution_x,
+                input.configs.resolution_y,
+            ))
+        } else {
+            None
+        };
+
+        if verbose {
+            println!("Thinning...");
+        }
+        let iterations = zhang_suen_thinning(&mut image, &|| reporter.check_cancelled())?;
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::U8;
+        output.configs.nodata = 255f64;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.configs.palette = "qual.plt".to_string();
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                if is_nodata(input.get_value(row, col), nodata) {
+                    output.set_value(row, col, 255f64);
+                } else {
+                    output.set_value(row, col, image.get_value(row, col) as f64);
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Thinning iterations: {}", iterations));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        drop(output);
+
+        if let Some(distances) = distances {
+            let mut width = Raster::initialize_using_file(&width_file, &input);
+            width.configs.data_type = DataType::F32;
+            width.configs.nodata = -32768f64;
+            width.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            width.configs.palette = "spectrum.plt".to_string();
+            width.reinitialize_values(-32768f64);
+            let half_cell = (input.configs.resolution_x + input.configs.resolution_y) / 4f64;
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    let d = distances.get_value(row, col);
+                    if image.get_value(row, col) == 1 && d.is_finite() {
+                        width.set_value(row, col, 2f64 * (d - half_cell));
+                    }
+                }
+            }
+            width.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            width.add_metadata_entry(format!("Input file: {}", input_file));
+            width.add_metadata_entry("Value: feature width at skeleton cells".to_string());
+            let _ = match width.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Width file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -2,20 +2,18 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 27/06/2017
-Last Modified: 30/01/2020
+Last Modified: 16/10/2026
 License: MIT
 */
 
+use super::convolution::{convolve, ConvolutionInput, Kernel, NodataMode};
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
-use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// This tool performs a 3 &times; 3 or 5 &times; 5 Sobel edge-detection filter on a raster image. The Sobel filter
 /// is similar to the `PrewittFilter`, in that it identifies areas of high slope in the input image through
@@ -43,6 +41,7 @@ use std::thread;
 ///
 /// The user must specify the `--variant`, including '3x3' and '5x5' variants. The user may also optionally
 /// clip the output image distribution tails by a specified amount (e.g. 1%).
+/// The `--compute=gpu` flag runs the filter on the GPU in builds with the `gpu` feature.
 ///
 /// # See Also
 /// `PrewittFilter`
@@ -166,66 +165,26 @@ impl WhiteboxTool for SobelFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
 
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut variant = "3x3".to_string();
         let mut clip_amount = 0.0;
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_string("variant") {
+            variant = value;
+            if variant.contains("5") {
+                variant = "5x5".to_string();
+            } else {
+                variant = "3x3".to_string();
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-variant" {
-                if keyval {
-                    variant = vec[1].to_string();
-                } else {
-                    variant = args[i + 1].to_string();
-                }
-                if variant.contains("5") {
-                    variant = "5x5".to_string();
-                } else {
-                    variant = "3x3".to_string();
-                }
-            } else if flag_val == "-clip" {
-                if keyval {
-                    clip_amount = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    clip_amount = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-                if clip_amount < 0.0 {
-                    clip_amount = 0.0;
-                }
+        }
+        if let Some(value) = parsed.get_f64("clip") {
+            clip_amount = value;
+            if clip_amount < 0.0 {
+                clip_amount = 0.0;
             }
         }
 
@@ -240,17 +199,8 @@ impl WhiteboxTool for SobelFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
-
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -277,116 +227,66 @@ impl WhiteboxTool for SobelFilter {
         output.configs.data_type = DataType::F32;
         output.configs.photometric_interp = PhotometricInterpretation::Continuous;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let variant = variant.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-                    Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-                } else {
-                    Box::new(|row: isize, col: isize| -> f64 {
-                        let value = input.get_value(row, col);
-                        if value != nodata {
-                            return value2i(value);
-                        }
-                        nodata
-                    })
-                };
-
-                let (mut slope_x, mut slope_y): (f64, f64);
-                let mut z: f64;
-                let mut zn: f64;
-
-                if variant.contains("3x3") {
-                    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
-                    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
-                    let mask_x = [1.0, 2.0, 1.0, 0.0, -1.0, -2.0, -1.0, 0.0];
-                    let mask_y = [1.0, 0.0, -1.0, -2.0, -1.0, 0.0, 1.0, 2.0];
-                    let num_pixels_in_filter = dx.len();
-
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            z = input_fn(row, col);
-                            if z != nodata {
-                                slope_x = 0.0;
-                                slope_y = 0.0;
-                                for i in 0..num_pixels_in_filter {
-                                    zn = input_fn(row + dy[i], col + dx[i]);
-                                    if zn == nodata {
-                                        zn = z; // replace it with z
-                                    }
-                                    slope_x += zn * mask_x[i];
-                                    slope_y += zn * mask_y[i];
-                                }
-                                data[col as usize] = (slope_x * slope_x + slope_y * slope_y).sqrt();
-                            }
-                        }
-                        tx1.send((row, data)).unwrap();
-                    }
-                } else {
-                    // 5x5
-                    let dx = [
-                        -2, -1, 0, 1, 2, -2, -1, 0, 1, 2, -2, -1, 0, 1, 2, -2, -1, 0, 1, 2, -2, -1,
-                        0, 1, 2,
-                    ];
-                    let dy = [
-                        -2, -2, -2, -2, -2, -1, -1, -1, -1, -1, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 2, 2,
-                        2, 2, 2,
-                    ];
-                    let mask_x = [
-                        2.0, 1.0, 0.0, -1.0, -2.0, 3.0, 2.0, 0.0, -2.0, -3.0, 4.0, 3.0, 0.0, -3.0,
-                        -4.0, 3.0, 2.0, 0.0, -2.0, -3.0, 2.0, 1.0, 0.0, -1.0, -2.0,
-                    ];
-                    let mask_y = [
-                        2.0, 3.0, 4.0, 3.0, 2.0, 1.0, 2.0, 3.0, 2.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0,
-                        -1.0, -2.0, -3.0, -2.0, -1.0, -2.0, -3.0, -4.0, -3.0, -2.0,
-                    ];
-                    let num_pixels_in_filter = dx.len();
-
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            z = input_fn(row, col);
-                            if z != nodata {
-                                slope_x = 0.0;
-                                slope_y = 0.0;
-                                for i in 0..num_pixels_in_filter {
-                                    zn = input_fn(row + dy[i], col + dx[i]);
-                                    if zn == nodata {
-                                        zn = z; // replace it with z
-                                    }
-                                    slope_x += zn * mask_x[i];
-                                    slope_y += zn * mask_y[i];
-                                }
-                                data[col as usize] = (slope_x * slope_x + slope_y * slope_y).sqrt();
-                            }
-                        }
-                        tx1.send((row, data)).unwrap();
-                    }
-                }
-            });
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (mask_x, mask_y, size) = if variant.contains("3x3") {
+            (
+                vec![-1.0, 0.0, 1.0, -2.0, 0.0, 2.0, -1.0, 0.0, 1.0],
+                vec![1.0, 2.0, 1.0, 0.0, 0.0, 0.0, -1.0, -2.0, -1.0],
+                3,
+            )
+        } else {
+            // 5x5
+            (
+                vec![
+                    2.0, 1.0, 0.0, -1.0, -2.0, 3.0, 2.0, 0.0, -2.0, -3.0, 4.0, 3.0, 0.0, -3.0,
+                    -4.0, 3.0, 2.0, 0.0, -2.0, -3.0, 2.0, 1.0, 0.0, -1.0, -2.0,
+                ],
+                vec![
+                    2.0, 3.0, 4.0, 3.0, 2.0, 1.0, 2.0, 3.0, 2.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+                    -1.0, -2.0, -3.0, -2.0, -1.0, -2.0, -3.0, -4.0, -3.0, -2.0,
+                ],
+                5,
+            )
+        };
+        let midpoint = (size / 2) as isize;
 
-        for row in 0..rows {
-            let data = rx.recv().expect("Error receiving data from thread.");
-            output.set_row_data(data.0, data.1);
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
+        // NoData neighbours are replaced with the value of the filtered cell.
+        let conv_input = Arc::new(ConvolutionInput::new(
+            &input,
+            if is_rgb_image { Some(value2i) } else { None },
+        ));
+        let mut slope_x = convolve(
+            &conv_input,
+            &Arc::new(Kernel::new(mask_x, size, size, midpoint, midpoint)),
+            NodataMode::ReplaceWithCentre,
+            nodata,
+            num_procs,
+            verbose,
+            reporter,
+        )?;
+        let slope_y = convolve(
+            &conv_input,
+            &Arc::new(Kernel::new(mask_y, size, size, midpoint, midpoint)),
+            NodataMode::ReplaceWithCentre,
+            nodata,
+            num_procs,
+            verbose,
+            reporter,
+        )?;
+        for row in 0..rows as usize {
+            reporter.check_cancelled()?;
+            for col in 0..columns as usize {
+                if !is_nodata(slope_x[row][col], nodata) {
+                    slope_x[row][col] = (slope_x[row][col] * slope_x[row][col]
+                        + slope_y[row][col] * slope_y[row][col])
+                        .sqrt();
                 }
             }
         }
+        for (row, data) in slope_x.into_iter().enumerate() {
+            output.set_row_data(row as isize, data);
+        }
 
         if clip_amount > 0.0 {
             if verbose {
@@ -0,0 +1,618 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+const BLUE: usize = 0;
+const GREEN: usize = 1;
+const RED: usize = 2;
+const REDEDGE: usize = 3;
+const NIR: usize = 4;
+const SWIR1: usize = 5;
+const SWIR2: usize = 6;
+const NUM_BANDS: usize = 7;
+const BAND_NAMES: [&str; NUM_BANDS] = ["blue", "green", "red", "rededge", "nir", "swir1", "swir2"];
+
+struct SpectralIndex {
+    name: &'static str,
+    bands: &'static [usize],
+    formula: fn(&[f64; NUM_BANDS]) -> f64,
+}
+
+fn nd(x: f64, y: f64) -> f64 {
+    if x + y != 0f64 {
+        (x - y) / (x + y)
+    } else {
+        f64::NAN
+    }
+}
+
+const INDICES: [SpectralIndex; 15] = [
+    SpectralIndex { name: "NDVI", bands: &[NIR, RED], formula: |b| nd(b[NIR], b[RED]) },
+    SpectralIndex { name: "GNDVI", bands: &[NIR, GREEN], formula: |b| nd(b[NIR], b[GREEN]) },
+    SpectralIndex { name: "NDRE", bands: &[NIR, REDEDGE], formula: |b| nd(b[NIR], b[REDEDGE]) },
+    SpectralIndex {
+        name: "EVI",
+        bands: &[NIR, RED, BLUE],
+        formula: |b| 2.5 * (b[NIR] - b[RED]) / (b[NIR] + 6.0 * b[RED] - 7.5 * b[BLUE] + 1.0),
+    },
+    SpectralIndex {
+        name: "EVI2",
+        bands: &[NIR, RED],
+        formula: |b| 2.5 * (b[NIR] - b[RED]) / (b[NIR] + 2.4 * b[RED] + 1.0),
+    },
+    SpectralIndex {
+        name: "SAVI",
+        bands: &[NIR, RED],
+        formula: |b| 1.5 * (b[NIR] - b[RED]) / (b[NIR] + b[RED] + 0.5),
+    },
+    SpectralIndex {
+        name: "MSAVI",
+        bands: &[NIR, RED],
+        formula: |b| {
+            let a = 2.0 * b[NIR] + 1.0;
+            (a - (a * a - 8.0 * (b[NIR] - b[RED])).sqrt()) / 2.0
+        },
+    },
+    SpectralIndex {
+        name: "OSAVI",
+        bands: &[NIR, RED],
+        formula: |b| (b[NIR] - b[RED]) / (b[NIR] + b[RED] + 0.16),
+    },
+    SpectralIndex { name: "NDWI", bands: &[GREEN, NIR], formula: |b| nd(b[GREEN], b[NIR]) },
+    SpectralIndex { name: "MNDWI", bands: &[GREEN, SWIR1], formula: |b| nd(b[GREEN], b[SWIR1]) },
+    SpectralIndex { name: "NDSI", bands: &[GREEN, SWIR1], formula: |b| nd(b[GREEN], b[SWIR1]) },
+    SpectralIndex { name: "NDMI", bands: &[NIR, SWIR1], formula: |b| nd(b[NIR], b[SWIR1]) },
+    SpectralIndex { name: "NBR", bands: &[NIR, SWIR2], formula: |b| nd(b[NIR], b[SWIR2]) },
+    SpectralIndex { name: "NBR2", bands: &[SWIR1, SWIR2], formula: |b| nd(b[SWIR1], b[SWIR2]) },
+    SpectralIndex {
+        name: "BSI",
+        bands: &[SWIR1, RED, NIR, BLUE],
+        formula: |b| nd(b[SWIR1] + b[RED], b[NIR] + b[BLUE]),
+    },
+];
+
+/// Returns the band number of each named band for a sensor, or None if the sensor is unknown. Zero
+/// indicates that the sensor lacks the band.
+fn sensor_bands(sensor: &str) -> Option<[usize; NUM_BANDS]> {
+    // blue, green, red, rededge, nir, swir1, swir2
+    match sensor {
+        "landsat8" | "landsat9" => Some([2, 3, 4, 0, 5, 6, 7]),
+        "landsat5" | "landsat7" => Some([1, 2, 3, 0, 4, 5, 7]),
+        "sentinel2" => Some([2, 3, 4, 5, 8, 11, 12]),
+        "modis" => Some([3, 4, 1, 0, 2, 6, 7]),
+        _ => None,
+    }
+}
+
+/// This tool computes one or more spectral indices from the bands of a multispectral image in a single
+/// pass. The input bands (`--inputs`) are specified as a list of single-band raster files, in band order,
+/// and the band numbers used by the tool are positions within this list, starting at 1. The bands used
+/// by each index are located either with a sensor preset (`--sensor`) or with an explicit band mapping
+/// (`--bands`) of the form `"red=4;nir=5"`, which takes precedence over the preset. The band names are
+/// blue, green, red, rededge, nir, swir1 (approx. 1.6 &mu;m) and swir2 (approx. 2.2 &mu;m). The presets
+/// are:
+///
+/// | Sensor      | Blue | Green | Red | Red edge | NIR | SWIR1 | SWIR2 |
+/// |-------------|------|-------|-----|----------|-----|-------|-------|
+/// | `landsat8`, `landsat9` (OLI) | 2 | 3 | 4 | - | 5 | 6 | 7 |
+/// | `landsat5`, `landsat7` (TM, ETM+) | 1 | 2 | 3 | - | 4 | 5 | 7 |
+/// | `sentinel2` (MSI) | 2 | 3 | 4 | 5 | 8 | 11 | 12 |
+/// | `modis` (MOD09) | 3 | 4 | 1 | - | 2 | 6 | 7 |
+///
+/// The `sentinel2` preset assumes the twelve Level-2A bands, B1 to B12 with B8A following B8 and without
+/// B10, so that bands B11 and B12 are in positions 11 and 12. Entries in the list of inputs may be left
+/// empty, e.g. `";;green.tif;red.tif;nir.tif"`, and only those bands that are needed by the requested
+/// indices are read.
+///
+/// The indices (`--indices`) are specified as a list of names, or `all` for every index that can be
+/// calculated from the mapped bands:
+///
+/// | Index | Name | Formula |
+/// |-------|------|---------|
+/// | NDVI  | Normalized difference vegetation index | (NIR - Red) / (NIR + Red) |
+/// | GNDVI | Green NDVI | (NIR - Green) / (NIR + Green) |
+/// | NDRE  | Normalized difference red edge index | (NIR - RedEdge) / (NIR + RedEdge) |
+/// | EVI   | Enhanced vegetation index | 2.5 (NIR - Red) / (NIR + 6 Red - 7.5 Blue + 1) |
+/// | EVI2  | Two-band EVI | 2.5 (NIR - Red) / (NIR + 2.4 Red + 1) |
+/// | SAVI  | Soil-adjusted vegetation index | 1.5 (NIR - Red) / (NIR + Red + 0.5) |
+/// | MSAVI | Modified SAVI | (2 NIR + 1 - sqrt((2 NIR + 1)^2 - 8 (NIR - Red))) / 2 |
+/// | OSAVI | Optimized SAVI | (NIR - Red) / (NIR + Red + 0.16) |
+/// | NDWI  | Normalized difference water index (McFeeters) | (Green - NIR) / (Green + NIR) |
+/// | MNDWI | Modified NDWI | (Green - SWIR1) / (Green + SWIR1) |
+/// | NDSI  | Normalized difference snow index | (Green - SWIR1) / (Green + SWIR1) |
+/// | NDMI  | Normalized difference moisture index | (NIR - SWIR1) / (NIR + SWIR1) |
+/// | NBR   | Normalized burn ratio | (NIR - SWIR2) / (NIR + SWIR2) |
+/// | NBR2  | Normalized burn ratio 2 | (SWIR1 - SWIR2) / (SWIR1 + SWIR2) |
+/// | BSI   | Bare soil index | ((SWIR1 + Red) - (NIR + Blue)) / ((SWIR1 + Red) + (NIR + Blue)) |
+///
+/// Indices with constant terms, i.e. EVI, EVI2, SAVI, MSAVI and OSAVI, require surface reflectance values
+/// between 0 and 1. Digital numbers are converted to reflectance as *scale* &times; DN + *offset*, using
+/// the `--scale` and `--offset` parameters, e.g. a scale of 0.0000275 and an offset of -0.2 for Landsat
+/// Collection 2 Level-2 products, or a scale of 0.0001 for Sentinel-2 Level-2A products.
+///
+/// One output raster is created for each index, in the output directory (`--outdir`), which defaults to
+/// the working directory, and named using the pattern PREFIX_INDEX, e.g. `scene_NDVI.tif`; without a
+/// `--prefix`, the files are simply named after the index. Cells for which any of the bands is NoData,
+/// or for which the index is undefined, e.g. because of division by zero, are NoData in the outputs.
+///
+/// # See Also
+/// `NormalizedDifferenceIndex`, `SurfaceWaterExtraction`
+pub struct SpectralIndices {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SpectralIndices {
+    pub fn new() -> SpectralIndices {
+        // public constructor
+        let name = "SpectralIndices".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Calculates one or more named spectral indices (NDVI, NDWI, NBR, EVI...) from the bands of a multispectral image.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Band Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input band raster files, in band order.".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Sensor".to_owned(),
+            flags: vec!["--sensor".to_owned()],
+            description: "Sensor band numbering preset (optional).".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "landsat8".to_owned(),
+                "landsat9".to_owned(),
+                "landsat7".to_owned(),
+                "landsat5".to_owned(),
+                "sentinel2".to_owned(),
+                "modis".to_owned(),
+            ]),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Band Mapping".to_owned(),
+            flags: vec!["--bands".to_owned()],
+            description: "Band numbers of named bands, e.g. 'red=4;nir=5', overriding the sensor preset (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Indices".to_owned(),
+            flags: vec!["--indices".to_owned()],
+            description: "Names of the indices to calculate, e.g. 'NDVI;NBR', or 'all'.".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: Some("NDVI".to_owned()),
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reflectance Scale Factor".to_owned(),
+            flags: vec!["--scale".to_owned()],
+            description: "Scale factor used to convert band values to reflectance.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reflectance Offset".to_owned(),
+            flags: vec!["--offset".to_owned()],
+            description: "Offset used to convert band values to reflectance.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Directory".to_owned(),
+            flags: vec!["--outdir".to_owned()],
+            description: "Output directory (optional; defaults to the working directory).".to_owned(),
+            parameter_type: ParameterType::Directory,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File Name Prefix".to_owned(),
+            flags: vec!["--prefix".to_owned()],
+            description: "Output file name prefix (optional).".to_owned(),
+            parameter_type: ParameterType::String,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='b1.tif;b2.tif;b3.tif;b4.tif;b5.tif;b6.tif;b7.tif' --sensor=landsat8 --indices='NDVI;NBR;MNDWI' --scale=0.0000275 --offset=-0.2 --prefix=scene
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='red.tif;nir.tif' --bands='red=1;nir=2' --indices=all",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SpectralIndices {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SpectralIndices {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files_str = parsed.get_string("inputs").unwrap_or_default();
+        let mut sensor = String::new();
+        let mut band_mapping = String::new();
+        let mut indices_str = String::from("NDVI");
+        let scale = parsed.get_f64("scale").unwrap_or(1f64);
+        let offset = parsed.get_f64("offset").unwrap_or(0f64);
+        let mut output_directory = parsed.get_string("outdir").unwrap_or_default();
+        let prefix = parsed.get_string("prefix").unwrap_or_default();
+
+        if let Some(value) = parsed.get_string("sensor") {
+            sensor = value.to_lowercase().replace(" ", "").replace("-", "");
+        }
+        if let Some(value) = parsed.get_string("bands") {
+            band_mapping = value.to_lowercase();
+        }
+        if let Some(value) = parsed.get_string("indices") {
+            indices_str = value.to_uppercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+
+        let mut input_files = input_files_str.split(";").collect::<Vec<&str>>();
+        if input_files.len() == 1 {
+            input_files = input_files_str.split(",").collect::<Vec<&str>>();
+        }
+
+        // Locate the named bands, as positions within the list of inputs.
+        let mut band_numbers = [0usize; NUM_BANDS];
+        if !sensor.is_empty() {
+            band_numbers = match sensor_bands(&sensor) {
+                Some(b) => b,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Unrecognized sensor '{}'; use one of landsat5, landsat7, landsat8, landsat9, sentinel2 or modis.", sensor),
+                    ))
+                }
+            };
+        }
+        for entry in band_mapping.split(|c| c == ';' || c == ',') {
+            if entry.trim().is_empty() {
+                continue;
+            }
+            let parts = entry.split("=").map(|s| s.trim()).collect::<Vec<&str>>();
+            let band = BAND_NAMES.iter().position(|n| parts.len() == 2 && *n == parts[0]);
+            let number = if parts.len() == 2 { parts[1].parse::<usize>().ok() } else { None };
+            match (band, number) {
+                (Some(band), Some(number)) => band_numbers[band] = number,
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Invalid band mapping entry '{}'; entries take the form name=number, with the names {}.", entry, BAND_NAMES.join(", ")),
+                    ))
+                }
+            }
+        }
+        for band in 0..NUM_BANDS {
+            let n = band_numbers[band];
+            if n > input_files.len() || (n > 0 && input_files[n - 1].trim().is_empty()) {
+                // the band is not supplied
+                band_numbers[band] = 0;
+            }
+        }
+
+        // Which indices are to be calculated?
+        let mut selected: Vec<usize> = vec![];
+        if indices_str.trim() == "ALL" {
+            for i in 0..INDICES.len() {
+                if INDICES[i].bands.iter().all(|&b| band_numbers[b] > 0) {
+                    selected.push(i);
+                }
+            }
+            if selected.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "None of the indices can be calculated from the mapped bands.",
+                ));
+            }
+        } else {
+            for name in indices_str.split(|c: char| c == ';' || c == ',' || c.is_whitespace()) {
+                if name.is_empty() {
+                    continue;
+                }
+                let i = match INDICES.iter().position(|index| index.name == name) {
+                    Some(i) => i,
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!(
+                                "Unrecognized index '{}'; available indices are {}.",
+                                name,
+                                INDICES.iter().map(|index| index.name).collect::<Vec<&str>>().join(", ")
+                            ),
+                        ))
+                    }
+                };
+                for &b in INDICES[i].bands {
+                    if band_numbers[b] == 0 {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("The {} index requires the {} band, which is not mapped to an input file.", name, BAND_NAMES[b]),
+                        ));
+                    }
+                }
+                if !selected.contains(&i) {
+                    selected.push(i);
+                }
+            }
+            if selected.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidInput, "No indices were specified."));
+            }
+        }
+
+        // Read the needed bands only; several named bands may share a file.
+        let mut needed = vec![false; NUM_BANDS];
+        for &i in &selected {
+            for &b in INDICES[i].bands {
+                needed[b] = true;
+            }
+        }
+        let mut band_slot = [usize::MAX; NUM_BANDS];
+        let mut slot_numbers: Vec<usize> = vec![];
+        let mut rasters: Vec<Raster> = vec![];
+        for band in 0..NUM_BANDS {
+            if !needed[band] {
+                continue;
+            }
+            let n = band_numbers[band];
+            if let Some(slot) = slot_numbers.iter().position(|&s| s == n) {
+                band_slot[band] = slot;
+                continue;
+            }
+            let input_file = resolve_input_path(input_files[n - 1].trim(), working_directory)?;
+            if verbose {
+                println!("Reading {} band ({})...", BAND_NAMES[band], input_file);
+            }
+            let raster = Raster::new(&input_file, "r")?;
+            if !rasters.is_empty()
+                && (raster.configs.rows != rasters[0].configs.rows
+                    || raster.configs.columns != rasters[0].configs.columns)
+            {
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
+            }
+            band_slot[band] = rasters.len();
+            slot_numbers.push(n);
+            rasters.push(raster);
+        }
+
+        if output_directory.is_empty() {
+            output_directory = working_directory.to_string();
+        }
+        if !output_directory.is_empty()
+            && !output_directory.ends_with(&sep)
+            && !output_directory.ends_with("/")
+        {
+            output_directory.push_str(&sep);
+        }
+        let extension = path::Path::new(&rasters[0].file_name)
+            .extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or("tif")
+            .to_string();
+        let output_files = selected
+            .iter()
+            .map(|&i| {
+                if prefix.is_empty() {
+                    format!("{}{}.{}", output_directory, INDICES[i].name, extension)
+                } else {
+                    format!("{}{}_{}.{}", output_directory, prefix, INDICES[i].name, extension)
+                }
+            })
+            .collect::<Vec<String>>();
+
+        let start = Instant::now();
+
+        let rows = rasters[0].configs.rows as isize;
+        let columns = rasters[0].configs.columns as isize;
+        let out_nodata = -32768f64;
+        let rasters = Arc::new(rasters);
+        let selected = Arc::new(selected);
+
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let rasters = rasters.clone();
+            let selected = selected.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let nodata = rasters.iter().map(|r| r.configs.nodata).collect::<Vec<f64>>();
+                let mut values = vec![0f64; rasters.len()];
+                let mut b = [0f64; NUM_BANDS];
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![vec![out_nodata; columns as usize]; selected.len()];
+                    for col in 0..columns {
+                        let mut any_nodata = false;
+                        for s in 0..rasters.len() {
+                            values[s] = rasters[s].get_value(row, col);
+                            if is_nodata(values[s], nodata[s]) {
+                                any_nodata = true;
+                                break;
+                            }
+                        }
+                        if any_nodata {
+                            continue;
+                        }
+                        for band in 0..NUM_BANDS {
+                            if band_slot[band] != usize::MAX {
+                                b[band] = scale * values[band_slot[band]] + offset;
+                            }
+                        }
+                        for (k, &i) in selected.iter().enumerate() {
+                            let z = (INDICES[i].formula)(&b);
+                            if z.is_finite() {
+                                data[k][col as usize] = z;
+                            }
+                        }
+                    }
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut outputs = vec![];
+        for k in 0..selected.len() {
+            let mut output = Raster::initialize_using_file(&output_files[k], &rasters[0]);
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.data_type = DataType::F32;
+            output.configs.nodata = out_nodata;
+            output.configs.palette = "grey.plt".to_string();
+            outputs.push(output);
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            for (k, d) in data.into_iter().enumerate() {
+                outputs[k].set_row_data(row, d);
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        if verbose {
+            println!("Saving data...")
+        };
+        for (k, mut output) in outputs.into_iter().enumerate() {
+            let index = &INDICES[selected[k]];
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Index: {}", index.name));
+            for &b in index.bands {
+                output.add_metadata_entry(format!(
+                    "{} band: {}",
+                    BAND_NAMES[b],
+                    rasters[band_slot[b]].file_name
+                ));
+            }
+            output.add_metadata_entry(format!("Reflectance scale: {}, offset: {}", scale, offset));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written: {}", output_files[k])
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -8,13 +8,12 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to split a red-green-blue (RGB) colour-composite image into three separate bands of
 /// multi-spectral imagery. The user must specify the input image (`--input`) and output red, green, blue images
@@ -145,53 +144,13 @@ impl WhiteboxTool for SplitColourComposite {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut red_file = String::new();
-        let mut green_file = String::new();
-        let mut blue_file = String::new();
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-red" {
-                red_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-green" {
-                green_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-blue" {
-                blue_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut red_file = parsed.get_string("red").unwrap_or_default();
+        let mut green_file = parsed.get_string("green").unwrap_or_default();
+        let mut blue_file = parsed.get_string("blue").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -204,23 +163,13 @@ impl WhiteboxTool for SplitColourComposite {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !red_file.contains(&sep) && !red_file.contains("/") {
-            red_file = format!("{}{}", working_directory, red_file);
-        }
-        if !green_file.contains(&sep) && !green_file.contains("/") {
-            green_file = format!("{}{}", working_directory, green_file);
-        }
-        if !blue_file.contains(&sep) && !blue_file.contains("/") {
-            blue_file = format!("{}{}", working_directory, blue_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        red_file = resolve_path(&red_file, working_directory);
+        green_file = resolve_path(&green_file, working_directory);
+        blue_file = resolve_path(&blue_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -235,17 +184,13 @@ impl WhiteboxTool for SplitColourComposite {
         let nodata = input.configs.nodata;
         let output_nodata = -32768f64;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut in_val: f64;
                 let mut val: u32;
                 let (mut red, mut green, mut blue): (u32, u32, u32);
@@ -255,7 +200,7 @@ impl WhiteboxTool for SplitColourComposite {
                     let mut data_b = vec![output_nodata; columns as usize];
                     for col in 0..columns {
                         in_val = input.get_value(row, col);
-                        if in_val != nodata {
+                        if !is_nodata(in_val, nodata) {
                             val = in_val as u32;
                             red = val & 0xFF;
                             green = (val >> 8) & 0xFF;
@@ -265,7 +210,9 @@ impl WhiteboxTool for SplitColourComposite {
                             data_b[col as usize] = blue as f64;
                         }
                     }
-                    tx.send((row, data_r, data_g, data_b)).unwrap();
+                    if tx.send((row, data_r, data_g, data_b)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -290,10 +237,11 @@ impl WhiteboxTool for SplitColourComposite {
             output_r.set_row_data(data.0, data.1);
             output_g.set_row_data(data.0, data.2);
             output_b.set_row_data(data.0, data.3);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,551 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::structures::{Array2D, Point2D};
+use whitebox_raster::*;
+use whitebox_vector::*;
+use crate::tools::*;
+use std::collections::HashMap;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool extracts class boundaries, such as waterlines and snowlines, from a continuous image
+/// band (`--input`), e.g. a water index (NDWI) or a snow index (NDSI), to sub-pixel precision, and
+/// writes them as smooth vector lines (`--output`). Boundaries traced along the edges of the cells of
+/// a classified raster are stair-stepped and can only be located to within a cell; those traced on the
+/// continuous band using [marching squares](https://en.wikipedia.org/wiki/Marching_squares) instead
+/// pass between the cell centres, at the positions found by linear interpolation of the band values.
+///
+/// The boundary is the contour of the band at a threshold value. If a binary classification raster
+/// (`--classes`) is supplied, in which positive values are the class of interest (e.g. water) and all
+/// other valid cells are background, the boundary is only traced within `--search` cells of the
+/// classified boundary, which excludes isolated noise and misclassified cells elsewhere in the image.
+/// The threshold then varies along the boundary: at each cell, it is the midpoint between the mean
+/// band values of the class and background cells within the search window, which adapts it to local
+/// changes in illumination, turbidity, or wetness. Where the window does not contain both classes, the
+/// global threshold is used, which is the value of `--threshold` if specified, or otherwise the midpoint
+/// between the mean band values of all of the class and background cells that lie on the classified
+/// boundary. If no classification raster is supplied, `--threshold` is required and the boundary is
+/// traced everywhere in the image.
+///
+/// The vertices of the output lines can be further smoothed using a moving average of the given odd
+/// size (`--smooth`); a value of 1 disables smoothing. The output lines have a `LENGTH` attribute.
+///
+/// # See Also
+/// `ContoursFromRaster`, `RasterToVectorPolygons`, `NormalizedDifferenceIndex`
+pub struct SubpixelBoundary {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SubpixelBoundary {
+    pub fn new() -> SubpixelBoundary {
+        // public constructor
+        let name = "SubpixelBoundary".to_string();
+        let toolbox = "Image Processing Tools".to_string();
+        let description =
+            "Extracts class boundaries, such as shorelines, from a continuous band to sub-pixel precision as vector lines.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Band File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input continuous band raster file, e.g. a water index.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Classification File (optional)".to_owned(),
+            flags: vec!["--classes".to_owned()],
+            description: "Optional input binary classification raster file; positive values are the class of interest.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector lines file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Threshold (optional)".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Band value of the boundary; required if no classification raster is specified.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Search Distance (cells)".to_owned(),
+            flags: vec!["--search".to_owned()],
+            description: "Half-width of the search window around the classified boundary, in cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("2".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Smoothing Filter Size".to_owned(),
+            flags: vec!["--smooth".to_owned()],
+            description: "Size of the moving average applied to the line vertices (odd; 1 for none).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=ndwi.tif --classes=water.tif -o=shoreline.shp --search=3 --smooth=5",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SubpixelBoundary {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SubpixelBoundary {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut classes_file = parsed.get_string("classes").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut threshold: Option<f64> = None;
+        let mut search = parsed.get_i64("search").map(|v| v as isize).unwrap_or(2isize);
+        let mut smooth = parsed.get_usize("smooth")?.unwrap_or(3usize);
+
+        if let Some(value) = parsed.get_f64("threshold") {
+            threshold = Some(
+                value,
+            );
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if classes_file.is_empty() && threshold.is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either a classification raster (--classes) or a --threshold must be specified.",
+            ));
+        }
+        if search < 1 {
+            search = 1;
+        }
+        if smooth < 1 {
+            smooth = 1;
+        }
+        if smooth % 2 == 0 {
+            smooth += 1;
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let classes = if !classes_file.is_empty() {
+            classes_file = resolve_input_path(&classes_file, working_directory)?;
+            let classes = Raster::new(&classes_file, "r")?;
+            if classes.configs.rows != input.configs.rows
+                || classes.configs.columns != input.configs.columns
+            {
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input band and classification rasters must have the same number of rows and columns."));
+            }
+            warn_if_crs_differs(&classes, &input, verbose);
+            Some(classes)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        // The threshold surface, which is nodata outside of the boundary zone.
+        let mut thresholds: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut global_threshold = threshold.unwrap_or(0f64);
+        if let Some(classes) = &classes {
+            // -1 for nodata, 0 for background, and 1 for the class of interest
+            let class_nodata = classes.configs.nodata;
+            let mut class: Array2D<i8> = Array2D::new(rows, columns, -1i8, -1i8)?;
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    let c = classes.get_value(row, col);
+                    if !is_nodata(c, class_nodata) && !is_nodata(input.get_value(row, col), nodata) {
+                        class.set_value(row, col, if c > 0f64 { 1i8 } else { 0i8 });
+                    }
+                }
+            }
+            let (dx, dy) = ([1isize, 0, -1, 0], [0isize, 1, 0, -1]);
+            let mut boundary = vec![];
+            let (mut sums, mut counts) = ([0f64; 2], [0f64; 2]);
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    let c = class.get_value(row, col);
+                    if c < 0 {
+                        continue;
+                    }
+                    if (0..4).any(|n| {
+                        let cn = class.get_value(row + dy[n], col + dx[n]);
+                        cn >= 0 && cn != c
+                    }) {
+                        boundary.push((row, col));
+                        sums[c as usize] += input.get_value(row, col);
+                        counts[c as usize] += 1f64;
+                    }
+                }
+            }
+            if boundary.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The classification raster does not contain a boundary between classes.",
+                ));
+            }
+            if threshold.is_none() {
+                global_threshold = (sums[0] / counts[0] + sums[1] / counts[1]) / 2f64;
+            }
+            if verbose {
+                println!("Global threshold: {}", global_threshold);
+            }
+
+            let mut in_zone: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+            for &(row, col) in &boundary {
+                for r in (row - search)..=(row + search) {
+                    for c in (col - search)..=(col + search) {
+                        if class.get_value(r, c) >= 0 {
+                            in_zone.set_value(r, c, 1u8);
+                        }
+                    }
+                }
+            }
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    if in_zone.get_value(row, col) == 0 {
+                        continue;
+                    }
+                    let (mut s, mut n) = ([0f64; 2], [0f64; 2]);
+                    for r in (row - search)..=(row + search) {
+                        for c in (col - search)..=(col + search) {
+                            let cn = class.get_value(r, c);
+                            if cn >= 0 {
+                                s[cn as usize] += input.get_value(r, c);
+                                n[cn as usize] += 1f64;
+                            }
+                        }
+                    }
+                    let t = if n[0] > 0f64 && n[1] > 0f64 {
+                        (s[0] / n[0] + s[1] / n[1]) / 2f64
+                    } else {
+                        global_threshold
+                    };
+                    thresholds.set_value(row, col, t);
+                }
+            }
+        } else {
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    if !is_nodata(input.get_value(row, col), nodata) {
+                        thresholds.set_value(row, col, global_threshold);
+                    }
+                }
+            }
+        }
+
+        // Marching squares on the squares between the centres of four cells. Each segment joins
+        // two square edges, which are identified as 2 * (row * columns + col) for the edge from
+        // cell (row, col) to its eastern neighbour and that plus one for the edge to its southern
+        // neighbour.
+        let value = |row: isize, col: isize| -> Option<f64> {
+            let t = thresholds.get_value(row, col);
+            if is_nodata(t, nodata) {
+                return None;
+            }
+            Some(input.get_value(row, col) - t)
+        };
+        let res_x = input.configs.resolution_x;
+        let res_y = input.configs.resolution_y;
+        let west = input.configs.west;
+        let north = input.configs.north;
+        let edge_point = |edge: usize, f: &[f64; 4], r: isize, c: isize| -> Point2D {
+            // square corners: 0 = (r, c), 1 = (r, c + 1), 2 = (r + 1, c + 1), 3 = (r + 1, c)
+            let (a, b, (ra, ca), (rb, cb)) = match edge {
+                0 => (f[0], f[1], (r, c), (r, c + 1)),
+                1 => (f[1], f[2], (r, c + 1), (r + 1, c + 1)),
+                2 => (f[3], f[2], (r + 1, c), (r + 1, c + 1)),
+                _ => (f[0], f[3], (r, c), (r + 1, c)),
+            };
+            let t = a / (a - b);
+            let row = ra as f64 + t * (rb - ra) as f64;
+            let col = ca as f64 + t * (cb - ca) as f64;
+            Point2D::new(west + (col + 0.5) * res_x, north - (row + 0.5) * res_y)
+        };
+        let edge_id = |edge: usize, r: isize, c: isize| -> usize {
+            let (row, col, vertical) = match edge {
+                0 => (r, c, 0),
+                1 => (r, c + 1, 1),
+                2 => (r + 1, c, 0),
+                _ => (r, c, 1),
+            };
+            2 * (row * columns + col) as usize + vertical
+        };
+
+        let mut segments: Vec<(usize, usize)> = vec![]; // pairs of edge ids
+        let mut points: HashMap<usize, Point2D> = HashMap::new();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for r in 0..rows - 1 {
+            for c in 0..columns - 1 {
+                let f = match (value(r, c), value(r, c + 1), value(r + 1, c + 1), value(r + 1, c)) {
+                    (Some(a), Some(b), Some(d), Some(e)) => [a, b, d, e],
+                    _ => continue,
+                };
+                let mut case = 0usize;
+                for k in 0..4 {
+                    if f[k] >= 0f64 {
+                        case |= 1 << k;
+                    }
+                }
+                // the pairs of square edges that are joined
+                let pairs: &[(usize, usize)] = match case {
+                    0 | 15 => &[],
+                    1 | 14 => &[(3, 0)],
+                    2 | 13 => &[(0, 1)],
+                    3 | 12 => &[(3, 1)],
+                    4 | 11 => &[(1, 2)],
+                    6 | 9 => &[(0, 2)],
+                    7 | 8 => &[(3, 2)],
+                    5 | 10 => {
+                        // saddle; resolved by the mean of the corners
+                        let centre_inside = (f[0] + f[1] + f[2] + f[3]) / 4f64 >= 0f64;
+                        if (case == 5) == centre_inside {
+                            &[(3, 2), (0, 1)]
+                        } else {
+                            &[(3, 0), (1, 2)]
+                        }
+                    }
+                    _ => unreachable!(),
+                };
+                for &(e1, e2) in pairs {
+                    let (id1, id2) = (edge_id(e1, r, c), edge_id(e2, r, c));
+                    points.entry(id1).or_insert_with(|| edge_point(e1, &f, r, c));
+                    points.entry(id2).or_insert_with(|| edge_point(e2, &f, r, c));
+                    segments.push((id1, id2));
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 2).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Tracing boundaries", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Join the segments into lines.
+        let mut at_edge: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, &(a, b)) in segments.iter().enumerate() {
+            at_edge.entry(a).or_insert_with(Vec::new).push(i);
+            at_edge.entry(b).or_insert_with(Vec::new).push(i);
+        }
+        let mut used = vec![false; segments.len()];
+        // walks from an edge away from a segment, returning the edges visited
+        let walk = |from_segment: usize, edge: usize, used: &mut Vec<bool>| -> Vec<usize> {
+            let mut path = vec![];
+            let (mut seg, mut e) = (from_segment, edge);
+            loop {
+                let next = at_edge[&e].iter().cloned().find(|&s| s != seg && !used[s]);
+                match next {
+                    Some(s) => {
+                        used[s] = true;
+                        let (a, b) = segments[s];
+                        e = if a == e { b } else { a };
+                        seg = s;
+                        path.push(e);
+                    }
+                    None => break,
+                }
+            }
+            path
+        };
+
+        let mut output = Shapefile::new(&output_file, ShapeType::PolyLine)?;
+        output.projection = input.configs.coordinate_ref_system_wkt.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        output.attributes.add_field(&AttributeField::new(
+            "LENGTH",
+            FieldDataType::Real,
+            12u8,
+            3u8,
+        ));
+
+        let mut fid = 1i32;
+        for i in 0..segments.len() {
+            if used[i] {
+                continue;
+            }
+            used[i] = true;
+            let (a, b) = segments[i];
+            let mut edges = walk(i, a, &mut used);
+            edges.reverse();
+            edges.push(a);
+            edges.push(b);
+            edges.extend(walk(i, b, &mut used));
+            let closed = edges.len() > 2 && edges[0] == edges[edges.len() - 1];
+            let mut line: Vec<Point2D> = edges.iter().map(|e| points[e]).collect();
+
+            if smooth > 1 && line.len() > 2 {
+                let half = (smooth / 2) as isize;
+                let n = if closed { line.len() - 1 } else { line.len() } as isize;
+                let mut smoothed = line.clone();
+                for j in 0..n {
+                    if !closed && (j == 0 || j == n - 1) {
+                        continue;
+                    }
+                    let (mut sx, mut sy, mut count) = (0f64, 0f64, 0f64);
+                    for k in (j - half)..=(j + half) {
+                        let k = if closed {
+                            (k + n) % n
+                        } else if k < 0 || k >= n {
+                            continue;
+                        } else {
+                            k
+                        };
+                        sx += line[k as usize].x;
+                        sy += line[k as usize].y;
+                        count += 1f64;
+                    }
+                    smoothed[j as usize] = Point2D::new(sx / count, sy / count);
+                }
+                if closed {
+                    let last = smoothed.len() - 1;
+                    smoothed[last] = smoothed[0];
+                }
+                line = smoothed;
+            }
+
+            let length: f64 = line.windows(2).map(|w| w[0].distance(&w[1])).sum();
+            let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+            sfg.add_part(&line);
+            output.add_record(sfg);
+            output
+                .attributes
+                .add_record(vec![FieldData::Int(fid), FieldData::Real(length)], false);
+            fid += 1;
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Number of lines: {}", output.num_records);
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
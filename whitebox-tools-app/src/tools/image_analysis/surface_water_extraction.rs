@@ -0,0 +1,493 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{kittler_threshold, otsu_threshold};
+use whitebox_common::structures::Array2D;
+use whitebox_raster::*;
+use crate::tools::data_tools::RasterToVectorPolygons;
+use crate::tools::*;
+use std::collections::VecDeque;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool maps surface water, e.g. for flood response, by thresholding a single band (`--input`):
+/// either SAR backscatter, preferably speckle-filtered and in decibels, in which open water is dark
+/// because it reflects the radar signal away from the sensor (`--water=low`, the default), or an optical
+/// water index such as the NDWI or MNDWI, in which water is bright (`--water=high`).
+///
+/// Unless a `--threshold` is specified, it is found automatically from the histogram of the band using
+/// either the minimum error method of Kittler and Illingworth (1986) (`--method=kittler`, the default) or
+/// Otsu's (1979) method (`--method=otsu`). Otsu's method assumes that the two classes have similar sizes
+/// and spreads, and so it tends to misplace the threshold when water covers a small part of the image,
+/// whereas the Kittler-Illingworth method models classes of any size and spread.
+///
+/// Terrain causes false positives in SAR water maps, because areas in radar shadow on slopes facing
+/// away from the sensor are as dark as water. Two optional masks remove them, and the masked cells are
+/// also excluded from the threshold calculation:
+///
+/// - A height above the nearest drainage (HAND) raster (`--hand`), e.g. from `ElevationAboveStream`;
+///   cells more than `--max_hand` (default 15) above the drainage network are not water. This also
+///   removes dark, dry surfaces on uplands, such as tarmac and sand, in either kind of image.
+/// - A local incidence angle raster (`--incidence`), in degrees, e.g. from the SAR processor; cells with
+///   local incidence angles greater than `--max_incidence` (default 80), which are in or near radar
+///   shadow, are not water.
+///
+/// Water bodies smaller than `--min_area` (in map units squared; by default, no minimum) are removed.
+/// The output (`--output`) is a raster with the value 1 for water, 0 for land, and NoData where the input
+/// is NoData. Optionally, the water areas are also written as polygons (`--polygons`).
+///
+/// # References
+/// Kittler, J., & Illingworth, J. (1986). Minimum error thresholding. *Pattern Recognition*, 19(1), 41-47.
+///
+/// Otsu, N. (1979). A threshold selection method from gray-level histograms. *IEEE Transactions on
+/// Systems, Man, and Cybernetics*, 9(1), 62-66.
+///
+/// # See Also
+/// `SarSpeckleFilter`, `SarDecibelConversion`, `NormalizedDifferenceIndex`, `ElevationAboveStream`, `RasterToVectorPolygons`
+pub struct SurfaceWaterExtraction {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SurfaceWaterExtraction {
+    pub fn new() -> SurfaceWaterExtraction {
+        // public constructor
+        let name = "SurfaceWaterExtraction".to_string();
+        let toolbox = "Image Processing Tools/Classification".to_string();
+        let description =
+            "Maps surface water by automatically thresholding a SAR or water index band, with optional terrain masking.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Band File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input SAR backscatter or water index raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Water Mask File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output water mask raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Water Polygons File (optional)".to_owned(),
+            flags: vec!["--polygons".to_owned()],
+            description: "Optional output vector polygons file of the water areas.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Water Values".to_owned(),
+            flags: vec!["--water".to_owned()],
+            description: "Whether water has 'low' (SAR backscatter) or 'high' (water index) band values.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["low".to_owned(), "high".to_owned()]),
+            default_value: Some("low".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Thresholding Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Automatic thresholding method; either 'kittler' or 'otsu'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["kittler".to_owned(), "otsu".to_owned()]),
+            default_value: Some("kittler".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Threshold (optional)".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Optional band threshold, which overrides the automatic threshold.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input HAND File (optional)".to_owned(),
+            flags: vec!["--hand".to_owned()],
+            description: "Optional input height above nearest drainage raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum HAND".to_owned(),
+            flags: vec!["--max_hand".to_owned()],
+            description: "Maximum height above nearest drainage of water cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("15.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Local Incidence Angle File (optional)".to_owned(),
+            flags: vec!["--incidence".to_owned()],
+            description: "Optional input local incidence angle raster file, in degrees.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Local Incidence Angle".to_owned(),
+            flags: vec!["--max_incidence".to_owned()],
+            description: "Maximum local incidence angle of water cells, in degrees.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("80.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Water Body Area (optional)".to_owned(),
+            flags: vec!["--min_area".to_owned()],
+            description: "Minimum area of a water body, in map units squared.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=sigma0_vv_db.tif -o=water.tif --polygons=water.shp --hand=hand.tif --max_hand=10.0 --min_area=5000.0
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=mndwi.tif -o=water.tif --water=high --method=otsu",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SurfaceWaterExtraction {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SurfaceWaterExtraction {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let polygons_file = parsed.get_string("polygons").unwrap_or_default();
+        let mut water = String::from("low");
+        let mut method = String::from("kittler");
+        let mut threshold: Option<f64> = None;
+        let mut hand_file = parsed.get_string("hand").unwrap_or_default();
+        let max_hand = parsed.get_f64("max_hand").unwrap_or(15f64);
+        let mut incidence_file = parsed.get_string("incidence").unwrap_or_default();
+        let max_incidence = parsed.get_f64("max_incidence").unwrap_or(80f64);
+        let min_area = parsed.get_f64("min_area").unwrap_or(0f64);
+
+        if let Some(value) = parsed.get_string("water") {
+            water = value.to_lowercase();
+        }
+        if let Some(value) = parsed.get_string("method") {
+            method = value.to_lowercase();
+        }
+        if let Some(value) = parsed.get_f64("threshold") {
+            threshold = Some(
+                value,
+            );
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let water_is_low = if water.contains("low") {
+            true
+        } else if water.contains("high") {
+            false
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --water parameter must be either 'low' or 'high'.",
+            ));
+        };
+        let use_otsu = if method.contains("otsu") {
+            true
+        } else if method.contains("kittler") {
+            false
+        } else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --method parameter must be either 'kittler' or 'otsu'.",
+            ));
+        };
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let read_mask = |file_name: &mut String| -> Result<Option<Raster>, Error> {
+            if file_name.is_empty() {
+                return Ok(None);
+            }
+            *file_name = resolve_input_path(file_name, working_directory)?;
+            let r = Raster::new(file_name, "r")?;
+            if r.configs.rows as isize != rows || r.configs.columns as isize != columns {
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The masking rasters must have the same number of rows and columns as the input band."));
+            }
+            Ok(Some(r))
+        };
+        let hand = read_mask(&mut hand_file)?;
+        let incidence = read_mask(&mut incidence_file)?;
+
+        let start = Instant::now();
+
+        let nodata = input.configs.nodata;
+
+        // Terrain-masked cells are excluded from the threshold calculation and are never water.
+        let mut masked: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let mut num_masked = 0usize;
+        let mut values = vec![];
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if is_nodata(z, nodata) {
+                    continue;
+                }
+                let mut is_masked = false;
+                if let Some(hand) = &hand {
+                    let h = hand.get_value(row, col);
+                    if !is_nodata(h, hand.configs.nodata) && h > max_hand {
+                        is_masked = true;
+                    }
+                }
+                if let Some(incidence) = &incidence {
+                    let a = incidence.get_value(row, col);
+                    if !is_nodata(a, incidence.configs.nodata) && a > max_incidence {
+                        is_masked = true;
+                    }
+                }
+                if is_masked {
+                    masked.set_value(row, col, 1u8);
+                    num_masked += 1;
+                } else {
+                    values.push(z);
+                }
+            }
+        }
+
+        let threshold = match threshold {
+            Some(t) => t,
+            None => {
+                let t = if use_otsu {
+                    otsu_threshold(&values, 256)
+                } else {
+                    kittler_threshold(&values, 256)
+                };
+                match t {
+                    Some(t) => t,
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidInput,
+                            "A threshold could not be found; the band may not contain both water and land, in which case specify a --threshold.",
+                        ))
+                    }
+                }
+            }
+        };
+        drop(values);
+        if verbose {
+            println!("Threshold: {}", threshold);
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::U8;
+        output.configs.nodata = 255f64;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.configs.palette = "qual.plt".to_string();
+        output.reinitialize_values(255f64);
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if is_nodata(z, nodata) {
+                    continue;
+                }
+                let is_water = masked.get_value(row, col) == 0
+                    && if water_is_low { z <= threshold } else { z >= threshold };
+                output.set_value(row, col, if is_water { 1f64 } else { 0f64 });
+            }
+        }
+
+        // Remove the water bodies, i.e. 8-connected groups of water cells, that are too small.
+        let mut num_removed = 0usize;
+        let cell_area = input.configs.resolution_x * input.configs.resolution_y;
+        if min_area > cell_area {
+            let (dx, dy) = ([1isize, 1, 1, 0, -1, -1, -1, 0], [-1isize, 0, 1, 1, 1, 0, -1, -1]);
+            let mut visited: Array2D<u8> = Array2D::new(rows, columns, 0u8, 1u8)?;
+            let mut queue = VecDeque::new();
+            let mut cells = vec![];
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    if output.get_value(row, col) != 1f64 || visited.get_value(row, col) == 1 {
+                        continue;
+                    }
+                    cells.clear();
+                    visited.set_value(row, col, 1);
+                    queue.push_back((row, col));
+                    while let Some((r, c)) = queue.pop_front() {
+                        cells.push((r, c));
+                        for n in 0..8 {
+                            let (rn, cn) = (r + dy[n], c + dx[n]);
+                            if visited.get_value(rn, cn) == 0 && output.get_value(rn, cn) == 1f64 {
+                                visited.set_value(rn, cn, 1);
+                                queue.push_back((rn, cn));
+                            }
+                        }
+                    }
+                    if (cells.len() as f64) * cell_area < min_area {
+                        for &(r, c) in &cells {
+                            output.set_value(r, c, 0f64);
+                        }
+                        num_removed += 1;
+                    }
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Threshold: {}", threshold));
+        output.add_metadata_entry(format!("Water values: {}", if water_is_low { "low" } else { "high" }));
+        if !hand_file.is_empty() {
+            output.add_metadata_entry(format!("HAND file: {} (max. {})", hand_file, max_hand));
+        }
+        if !incidence_file.is_empty() {
+            output.add_metadata_entry(format!(
+                "Local incidence angle file: {} (max. {})",
+                incidence_file, max_incidence
+            ));
+        }
+        output.add_metadata_entry(format!("Terrain-masked cells: {}", num_masked));
+        output.add_metadata_entry(format!("Water bodies removed for area: {}", num_removed));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Terrain-masked cells: {}", num_masked);
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+        drop(output);
+
+        if !polygons_file.is_empty() {
+            if verbose {
+                println!("Creating water polygons...")
+            };
+            RasterToVectorPolygons::new().run(
+                vec![
+                    format!("--input={}", output_file),
+                    format!("--output={}", polygons_file),
+                ],
+                working_directory,
+                false,
+                reporter,
+            )?;
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
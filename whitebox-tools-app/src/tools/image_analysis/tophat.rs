@@ -9,15 +9,14 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::collections::VecDeque;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs either a white or black [top-hat transform](https://en.wikipedia.org/wiki/Top-hat_transform)
 /// on an input image. A top-hat transform is a common digital image processing operation used for various tasks, such
@@ -165,94 +164,24 @@ impl WhiteboxTool for TophatTransform {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size_x = 11usize;
-        let mut filter_size_y = 11usize;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size_x = parsed.get_usize("filterx")?.unwrap_or(11usize);
+        let mut filter_size_y = parsed.get_usize("filtery")?.unwrap_or(11usize);
         let mut variant = "white".to_string();
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_silent_f64("filter")? {
+            filter_size_x = value as usize;
+            filter_size_y = filter_size_x;
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-filter" {
-                if keyval {
-                    filter_size_x = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_x = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-                filter_size_y = filter_size_x;
-            } else if flag_val == "-filterx" {
-                if keyval {
-                    filter_size_x = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_x = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-filtery" {
-                if keyval {
-                    filter_size_y = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size_y = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-variant" {
-                if keyval {
-                    variant = vec[1].to_string();
-                } else {
-                    variant = args[i + 1].to_string();
-                }
-                if variant.to_lowercase().contains("b") {
-                    variant = "black".to_string();
-                } else {
-                    variant = "white".to_string();
-                }
+        if let Some(value) = parsed.get_string("variant") {
+            variant = value;
+            if variant.to_lowercase().contains("b") {
+                variant = "black".to_string();
+            } else {
+                variant = "white".to_string();
             }
         }
 
@@ -267,8 +196,6 @@ impl WhiteboxTool for TophatTransform {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         if filter_size_x < 3 {
             filter_size_x = 3;
         }
@@ -289,12 +216,8 @@ impl WhiteboxTool for TophatTransform {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -307,12 +230,8 @@ impl WhiteboxTool for TophatTransform {
         let rows = input.configs.rows as isize;
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         if variant == "white".to_string() {
             // first perform the erosion
@@ -320,7 +239,7 @@ impl WhiteboxTool for TophatTransform {
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut z_n, mut z): (f64, f64);
                     let mut min_val: f64;
                     let (mut start_col, mut end_col, mut start_row, mut end_row): (
@@ -341,7 +260,7 @@ impl WhiteboxTool for TophatTransform {
                                 min_val = f64::INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col + midpoint_x);
-                                    if z_n != nodata {
+                                    if !is_nodata(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -356,7 +275,7 @@ impl WhiteboxTool for TophatTransform {
                                     min_val = f64::INFINITY;
                                     for row2 in start_row..end_row + 1 {
                                         z_n = input.get_value(row2, col2);
-                                        if z_n != nodata {
+                                        if !is_nodata(z_n, nodata) {
                                             if z_n < min_val {
                                                 min_val = z_n;
                                             }
@@ -366,7 +285,7 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 min_val = f64::INFINITY;
                                 for i in 0..filter_size_x {
                                     if filter_min_vals[i] < min_val {
@@ -378,7 +297,9 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                         }
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -387,10 +308,11 @@ impl WhiteboxTool for TophatTransform {
             for row in 0..rows {
                 let data = rx.recv().expect("Error receiving data from thread.");
                 erosion.set_row_data(data.0, data.1);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress (Loop 1 of 2): {}%", progress);
+                        reporter.progress("Progress (Loop 1 of 2)", progress);
                         old_progress = progress;
                     }
                 }
@@ -403,7 +325,7 @@ impl WhiteboxTool for TophatTransform {
                 let input_data = input.clone();
                 let input = erosion.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut z_n, mut z): (f64, f64);
                     let mut max_val: f64;
                     let (mut start_col, mut end_col, mut start_row, mut end_row): (
@@ -424,7 +346,7 @@ impl WhiteboxTool for TophatTransform {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col + midpoint_x);
-                                    if z_n != nodata {
+                                    if !is_nodata(z_n, nodata) {
                                         if z_n > max_val {
                                             max_val = z_n;
                                         }
@@ -439,7 +361,7 @@ impl WhiteboxTool for TophatTransform {
                                     max_val = f64::NEG_INFINITY;
                                     for row2 in start_row..end_row + 1 {
                                         z_n = input.get_value(row2, col2);
-                                        if z_n != nodata {
+                                        if !is_nodata(z_n, nodata) {
                                             if z_n > max_val {
                                                 max_val = z_n;
                                             }
@@ -449,7 +371,7 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 max_val = f64::NEG_INFINITY;
                                 for i in 0..filter_size_x {
                                     if filter_max_vals[i] > max_val {
@@ -461,7 +383,9 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                         }
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -470,10 +394,11 @@ impl WhiteboxTool for TophatTransform {
             for row in 0..rows {
                 let data = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(data.0, data.1);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress (Loop 2 of 2): {}%", progress);
+                        reporter.progress("Progress (Loop 2 of 2)", progress);
                         old_progress = progress;
                     }
                 }
@@ -515,7 +440,7 @@ impl WhiteboxTool for TophatTransform {
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut z_n, mut z): (f64, f64);
                     let mut max_val: f64;
                     let (mut start_col, mut end_col, mut start_row, mut end_row): (
@@ -536,7 +461,7 @@ impl WhiteboxTool for TophatTransform {
                                 max_val = f64::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col + midpoint_x);
-                                    if z_n != nodata {
+                                    if !is_nodata(z_n, nodata) {
                                         if z_n > max_val {
                                             max_val = z_n;
                                         }
@@ -551,7 +476,7 @@ impl WhiteboxTool for TophatTransform {
                                     max_val = f64::NEG_INFINITY;
                                     for row2 in start_row..end_row + 1 {
                                         z_n = input.get_value(row2, col2);
-                                        if z_n != nodata {
+                                        if !is_nodata(z_n, nodata) {
                                             if z_n > max_val {
                                                 max_val = z_n;
                                             }
@@ -561,7 +486,7 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 max_val = f64::NEG_INFINITY;
                                 for i in 0..filter_size_x {
                                     if filter_max_vals[i] > max_val {
@@ -573,7 +498,9 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                         }
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -582,10 +509,11 @@ impl WhiteboxTool for TophatTransform {
             for row in 0..rows {
                 let data = rx.recv().expect("Error receiving data from thread.");
                 dilation.set_row_data(data.0, data.1);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress (Loop 1 of 2): {}%", progress);
+                        reporter.progress("Progress (Loop 1 of 2)", progress);
                         old_progress = progress;
                     }
                 }
@@ -598,7 +526,7 @@ impl WhiteboxTool for TophatTransform {
                 let input_data = input.clone();
                 let input = dilation.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut z_n, mut z): (f64, f64);
                     let mut min_val: f64;
                     let (mut start_col, mut end_col, mut start_row, mut end_row): (
@@ -619,7 +547,7 @@ impl WhiteboxTool for TophatTransform {
                                 min_val = f64::INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col + midpoint_x);
-                                    if z_n != nodata {
+                                    if !is_nodata(z_n, nodata) {
                                         if z_n < min_val {
                                             min_val = z_n;
                                         }
@@ -634,7 +562,7 @@ impl WhiteboxTool for TophatTransform {
                                     min_val = f64::INFINITY;
                                     for row2 in start_row..end_row + 1 {
                                         z_n = input.get_value(row2, col2);
-                                        if z_n != nodata {
+                                        if !is_nodata(z_n, nodata) {
                                             if z_n < min_val {
                                                 min_val = z_n;
                                             }
@@ -644,7 +572,7 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 min_val = f64::INFINITY;
                                 for i in 0..filter_size_x {
                                     if filter_min_vals[i] < min_val {
@@ -656,7 +584,9 @@ impl WhiteboxTool for TophatTransform {
                                 }
                             }
                         }
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -665,10 +595,11 @@ impl WhiteboxTool for TophatTransform {
             for row in 0..rows {
                 let data = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(data.0, data.1);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress (Loop 2 of 2): {}%", progress);
+                        reporter.progress("Progress (Loop 2 of 2)", progress);
                         old_progress = progress;
                     }
                 }
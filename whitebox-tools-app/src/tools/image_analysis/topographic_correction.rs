@@ -0,0 +1,628 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use whitebox_common::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool removes the effect of topography on the illumination of an image band (`--input`), so
+/// that slopes facing towards and away from the sun have similar values for similar surfaces, and
+/// detects the parts of the image that are in shadow. Topographic correction is an important
+/// pre-processing step before the classification of imagery of mountainous areas. The user must
+/// specify a digital elevation model (`--dem`) and the solar azimuth (`--azimuth`; degrees clockwise
+/// from north) and altitude (`--altitude`; the elevation of the sun above the horizon, in degrees) at
+/// the time of image acquisition, which are usually given in the image metadata.
+///
+/// The illumination (*IL*) of each grid cell is the cosine of the solar incidence angle on the
+/// terrain surface:
+///
+/// > *IL* = cos(*Z*) cos(*s*) + sin(*Z*) sin(*s*) cos(*Az* - *a*)
+///
+/// where *Z* is the solar zenith angle (90 - altitude), *Az* is the solar azimuth and *s* and *a* are
+/// the local slope gradient and aspect, calculated using Horn's (1981) 3rd-order finite difference
+/// method. One of the following correction methods (`--method`) is then applied to each image value
+/// (*L*):
+///
+/// - `c` (the default): the C-correction (Teillet et al., 1982), *L*' = *L* (cos(*Z*) + *c*) / (*IL* + *c*),
+///   where *c* = *a* / *b* is estimated from the linear regression *L* = *a* + *b* *IL*. The *c*
+///   parameter moderates the over-correction of weakly illuminated slopes by the cosine method.
+/// - `minnaert`: the Minnaert correction, *L*' = *L* (cos(*Z*) / *IL*)<sup>*k*</sup>, where the Minnaert
+///   constant *k* is estimated from the linear regression ln(*L*) = ln(*L*<sub>n</sub>) + *k* ln(*IL*).
+/// - `cosine`: the cosine correction, *L*' = *L* cos(*Z*) / *IL*, which assumes that the surface is a
+///   Lambertian reflector.
+///
+/// The regression parameters and their coefficients of determination are reported in verbose mode
+/// and stored in the output's metadata. The corrections assume that the image is dominated by a single
+/// land cover; when this is not the case, the tool may be applied to each land cover separately by
+/// setting the other land covers to NoData.
+///
+/// Grid cells that face away from the sun (*IL* &le; 0) are in *self-shadow*, and grid cells whose view of
+/// the sun is blocked by the surrounding terrain are in *cast shadow*; cast shadows are found by tracing
+/// a ray from each grid cell towards the sun. Shadowed areas are lit only by diffuse light, which the
+/// corrections do not model, and so their values are copied to the output unchanged and are excluded
+/// from the regressions. The shadows may be written to an optional raster (`--shadow_mask`), with values
+/// of 0 for illuminated cells, 1 for self-shadow and 2 for cast shadow, which can be used to exclude
+/// shadowed areas from a subsequent classification.
+///
+/// The image and DEM must share the same grid. If `--align=auto` is specified, a DEM that differs in
+/// extent or resolution is instead resampled and cropped to the grid of the image, using bilinear
+/// interpolation, with a warning. The Z conversion factor (`--zfactor`) is only important when the
+/// vertical and horizontal units of the DEM are not the same; if the DEM is in geographic coordinates
+/// and no factor is specified, one is calculated for each row as in the `Hillshade` tool. The output is
+/// of the 32-bit floating-point data type.
+///
+/// # References
+/// Horn, B. K. (1981). Hill shading and the reflectance map. *Proceedings of the IEEE*, 69(1), 14-47.
+///
+/// Meyer, P., Itten, K. I., Kellenberger, T., Sandmeier, S., & Sandmeier, R. (1993). Radiometric
+/// corrections of topographically induced effects on Landsat TM data in an alpine environment. *ISPRS
+/// Journal of Photogrammetry and Remote Sensing*, 48(4), 17-28.
+///
+/// Teillet, P. M., Guindon, B., & Goodenough, D. G. (1982). On the slope-aspect correction of
+/// multispectral scanner data. *Canadian Journal of Remote Sensing*, 8(2), 84-106.
+///
+/// # See Also
+/// `Hillshade`, `HorizonAngle`, `SpectralIndices`, `CorrectVignetting`
+pub struct TopographicCorrection {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TopographicCorrection {
+    pub fn new() -> TopographicCorrection {
+        // public constructor
+        let name = "TopographicCorrection".to_string();
+        let toolbox = "Image Processing Tools/Image Enhancement".to_string();
+        let description =
+            "Corrects the topographic illumination of an image band using a DEM and the solar geometry, and detects shadows."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Image File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input image band raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output corrected image raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Solar Azimuth (degrees)".to_owned(),
+            flags: vec!["--azimuth".to_owned()],
+            description: "Solar azimuth at the time of image acquisition, in degrees clockwise from north.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Solar Altitude (degrees)".to_owned(),
+            flags: vec!["--altitude".to_owned()],
+            description: "Solar altitude (elevation above the horizon) at the time of image acquisition, in degrees.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Correction Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "Correction method; one of 'c', 'minnaert', and 'cosine'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "c".to_owned(),
+                "minnaert".to_owned(),
+                "cosine".to_owned(),
+            ]),
+            default_value: Some("c".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Z Conversion Factor".to_owned(),
+            flags: vec!["--zfactor".to_owned()],
+            description:
+                "Optional multiplier for when the vertical and horizontal units are not the same."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Shadow Mask File (optional)".to_owned(),
+            flags: vec!["--shadow_mask".to_owned()],
+            description: "Optional output shadow raster file (0 = illuminated, 1 = self-shadow, 2 = cast shadow).".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Alignment".to_owned(),
+            flags: vec!["--align".to_owned()],
+            description: "Input alignment mode; 'auto' resamples the DEM to the grid of the image.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["none".to_owned(), "auto".to_owned()]),
+            default_value: Some("none".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=nir.tif --dem=dem.tif -o=nir_corrected.tif --azimuth=152.3 --altitude=38.6 --method=c --shadow_mask=shadows.tif", short_exe, name).replace("*", &sep);
+
+        TopographicCorrection {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TopographicCorrection {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let azimuth = parsed.get_f64("azimuth").unwrap_or(f64::NAN);
+        let altitude = parsed.get_f64("altitude").unwrap_or(f64::NAN);
+        let method = parsed.get_string("method").unwrap_or_default();
+        let z_factor = parsed.get_f64("zfactor").unwrap_or(-1f64);
+        let mut shadow_file = parsed.get_string("shadow_mask").unwrap_or_default();
+        let align = parsed.get_string("align").unwrap_or_default() == "auto";
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if altitude <= 0f64 || altitude > 90f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The solar altitude (--altitude) must be greater than 0 and no greater than 90 degrees.",
+            ));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        if !shadow_file.is_empty() {
+            shadow_file = resolve_path(&shadow_file, working_directory);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Raster::new(&input_file, "r")?;
+        let dem = read_secondary_raster(&dem_file, &input, align, true, verbose)?;
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let dem_nodata = dem.configs.nodata;
+        let res_x = dem.configs.resolution_x;
+        let res_y = dem.configs.resolution_y;
+
+        // Convert the elevations to the horizontal units.
+        let mut z_factor_array = Vec::with_capacity(rows as usize);
+        if dem.is_in_geographic_coordinates() && z_factor < 0.0 {
+            for row in 0..rows {
+                let lat = dem.get_y_from_row(row).to_radians();
+                z_factor_array.push(1.0 / (111320.0 * lat.cos()));
+            }
+        } else {
+            let z = if z_factor < 0.0 { 1.0 } else { z_factor };
+            z_factor_array = vec![z; rows as usize];
+        }
+        let mut elev: Array2D<f64> = Array2D::new(rows, columns, dem_nodata, dem_nodata)?;
+        let mut max_elev = f64::NEG_INFINITY;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let z = dem.get_value(row, col);
+                if !is_nodata(z, dem_nodata) {
+                    let z = z * z_factor_array[row as usize];
+                    elev.set_value(row, col, z);
+                    if z > max_elev {
+                        max_elev = z;
+                    }
+                }
+            }
+        }
+        drop(dem);
+        let elev = Arc::new(elev);
+
+        let az = azimuth.to_radians();
+        let alt = altitude.to_radians();
+        let cos_zenith = alt.sin();
+        // unit vector pointing towards the sun (east, north, up)
+        let sun = [az.sin() * alt.cos(), az.cos() * alt.cos(), alt.sin()];
+        let tan_alt = alt.tan();
+
+        // Calculate the illumination and find the shadows.
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let elev = elev.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+                let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+                let mut n = [0f64; 8];
+                let step = res_x.min(res_y);
+                // the displacement, in cells, of one step towards the sun
+                let step_col = step * az.sin() / res_x;
+                let step_row = -step * az.cos() / res_y;
+                // Returns the elevation at a fractional grid position, by bilinear interpolation.
+                let sample = |r: f64, c: f64| -> f64 {
+                    let r0 = r.floor();
+                    let c0 = c.floor();
+                    let (fr, fc) = (r - r0, c - c0);
+                    let (r0, c0) = (r0 as isize, c0 as isize);
+                    let z00 = elev.get_value(r0, c0);
+                    let z01 = elev.get_value(r0, c0 + 1);
+                    let z10 = elev.get_value(r0 + 1, c0);
+                    let z11 = elev.get_value(r0 + 1, c0 + 1);
+                    if is_nodata(z00, dem_nodata) || is_nodata(z01, dem_nodata) || is_nodata(z10, dem_nodata) || is_nodata(z11, dem_nodata) {
+                        return elev.get_value(r.round() as isize, c.round() as isize);
+                    }
+                    (z00 * (1f64 - fc) + z01 * fc) * (1f64 - fr) + (z10 * (1f64 - fc) + z11 * fc) * fr
+                };
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut illumination = vec![f64::NAN; columns as usize];
+                    let mut shadows = vec![255u8; columns as usize];
+                    for col in 0..columns {
+                        let z = elev.get_value(row, col);
+                        if is_nodata(z, dem_nodata) {
+                            continue;
+                        }
+                        for c in 0..8 {
+                            n[c] = elev.get_value(row + d_y[c], col + d_x[c]);
+                            if is_nodata(n[c], dem_nodata) {
+                                n[c] = z;
+                            }
+                        }
+                        let fx = (n[2] - n[4] + 2.0 * (n[1] - n[5]) + n[0] - n[6]) / (8f64 * res_x);
+                        let fy = (n[6] - n[4] + 2.0 * (n[7] - n[3]) + n[0] - n[2]) / (8f64 * res_y);
+                        // the cosine of the angle between the surface normal and the sun
+                        let il = (-fx * sun[0] - fy * sun[1] + sun[2]) / (fx * fx + fy * fy + 1f64).sqrt();
+                        illumination[col as usize] = il;
+                        if il <= 0f64 {
+                            shadows[col as usize] = 1u8;
+                            continue;
+                        }
+                        shadows[col as usize] = 0u8;
+                        // Trace a ray towards the sun until it is above the highest point in the DEM.
+                        let mut k = 1f64;
+                        loop {
+                            let ray_z = z + k * step * tan_alt;
+                            if ray_z > max_elev {
+                                break;
+                            }
+                            let r = row as f64 + k * step_row;
+                            let c = col as f64 + k * step_col;
+                            if r < 0f64 || c < 0f64 || r > (rows - 1) as f64 || c > (columns - 1) as f64 {
+                                break;
+                            }
+                            let zs = sample(r, c);
+                            if !is_nodata(zs, dem_nodata) && zs > ray_z {
+                                shadows[col as usize] = 2u8;
+                                break;
+                            }
+                            k += 1f64;
+                        }
+                    }
+                    if tx.send((row, illumination, shadows)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut illumination: Array2D<f64> = Array2D::new(rows, columns, f64::NAN, f64::NAN)?;
+        let mut shadows: Array2D<u8> = Array2D::new(rows, columns, 255u8, 255u8)?;
+        for r in 0..rows {
+            let (row, il, sh) = rx.recv().expect("Error receiving data from thread.");
+            illumination.set_row_data(row, il);
+            shadows.set_row_data(row, sh);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Calculating illumination", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Estimate the correction parameter from the illuminated cells.
+        let (mut n, mut sx, mut sy, mut sxx, mut sxy, mut syy) = (0f64, 0f64, 0f64, 0f64, 0f64, 0f64);
+        if method != "cosine" {
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    let z = input.get_value(row, col);
+                    if is_nodata(z, nodata) || shadows.get_value(row, col) != 0u8 {
+                        continue;
+                    }
+                    let il = illumination.get_value(row, col);
+                    let (x, y) = if method == "c" {
+                        (il, z)
+                    } else if z > 0f64 {
+                        (il.ln(), z.ln())
+                    } else {
+                        continue;
+                    };
+                    n += 1f64;
+                    sx += x;
+                    sy += y;
+                    sxx += x * x;
+                    sxy += x * y;
+                    syy += y * y;
+                }
+            }
+            if n < 3f64 || n * sxx - sx * sx <= 0f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "There are too few illuminated grid cells, or too little variation in illumination, to estimate the correction parameter.",
+                ));
+            }
+        }
+        let mut param = 0f64;
+        let mut r_sqr = 0f64;
+        if n > 0f64 {
+            let slope = (n * sxy - sx * sy) / (n * sxx - sx * sx);
+            let intercept = (sy - slope * sx) / n;
+            let denom = (n * sxx - sx * sx) * (n * syy - sy * sy);
+            r_sqr = if denom > 0f64 {
+                (n * sxy - sx * sy).powi(2) / denom
+            } else {
+                0f64
+            };
+            if method == "c" {
+                if slope > 0f64 {
+                    param = intercept / slope;
+                } else {
+                    // The image is not brighter on more strongly illuminated slopes.
+                    param = f64::INFINITY;
+                    if verbose {
+                        println!("Warning: The image values do not increase with illumination; no correction will be applied.");
+                    }
+                }
+            } else {
+                param = slope;
+            }
+            if verbose {
+                if method == "c" {
+                    println!("C-correction parameter (c): {:.4} (r-squared = {:.4}, n = {})", param, r_sqr, n);
+                } else {
+                    println!("Minnaert constant (k): {:.4} (r-squared = {:.4}, n = {})", param, r_sqr, n);
+                }
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        let (mut num_self, mut num_cast) = (0usize, 0usize);
+        for row in 0..rows {
+            let mut data = vec![nodata; columns as usize];
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if is_nodata(z, nodata) {
+                    continue;
+                }
+                let shadow = shadows.get_value(row, col);
+                if shadow != 0u8 {
+                    // shadowed and NoData DEM cells are not corrected
+                    data[col as usize] = z;
+                    if shadow == 1u8 {
+                        num_self += 1;
+                    } else if shadow == 2u8 {
+                        num_cast += 1;
+                    }
+                    continue;
+                }
+                let il = illumination.get_value(row, col);
+                data[col as usize] = if method == "c" {
+                    if param.is_finite() {
+                        z * (cos_zenith + param) / (il + param)
+                    } else {
+                        z
+                    }
+                } else if method == "minnaert" {
+                    z * (cos_zenith / il).powf(param)
+                } else {
+                    z * cos_zenith / il
+                };
+            }
+            output.set_row_data(row, data);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Correcting image", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if verbose {
+            let num_cells = (rows * columns) as f64;
+            println!(
+                "Self-shadow: {:.2}% of cells; cast shadow: {:.2}% of cells",
+                100f64 * num_self as f64 / num_cells,
+                100f64 * num_cast as f64 / num_cells
+            );
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("DEM file: {}", dem_file));
+        output.add_metadata_entry(format!("Solar azimuth: {}", azimuth));
+        output.add_metadata_entry(format!("Solar altitude: {}", altitude));
+        output.add_metadata_entry(format!("Method: {}", method));
+        if method == "c" {
+            output.add_metadata_entry(format!("C-correction parameter: {} (r-squared = {})", param, r_sqr));
+        } else if method == "minnaert" {
+            output.add_metadata_entry(format!("Minnaert constant: {} (r-squared = {})", param, r_sqr));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !shadow_file.is_empty() {
+            let mut mask = Raster::initialize_using_file(&shadow_file, &input);
+            mask.configs.data_type = DataType::U8;
+            mask.configs.nodata = 255f64;
+            mask.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            mask.configs.palette = "qual.plt".to_string();
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                let mut data = vec![255f64; columns as usize];
+                for col in 0..columns {
+                    if !is_nodata(input.get_value(row, col), nodata) {
+                        data[col as usize] = shadows.get_value(row, col) as f64;
+                    }
+                }
+                mask.set_row_data(row, data);
+            }
+            mask.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            mask.add_metadata_entry(format!("DEM file: {}", dem_file));
+            mask.add_metadata_entry("Values: 0 = illuminated, 1 = self-shadow, 2 = cast shadow".to_string());
+            let _ = match mask.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Shadow mask written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
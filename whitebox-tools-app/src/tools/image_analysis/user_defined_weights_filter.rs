@@ -2,13 +2,13 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 26/04/2018
-Last Modified: 22/10/2019
+Last Modified: 16/10/2026
 License: MIT
 */
 
+use super::convolution::{convolve, ConvolutionInput, Kernel, NodataMode};
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
@@ -17,9 +17,7 @@ use std::io::prelude::*;
 use std::io::BufReader;
 use std::io::{Error, ErrorKind};
 use std::path;
-use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
 
 /// NoData values in the input image are ignored during the convolution operation.
 /// This can lead to unexpected behavior at the edges of images (since the default behavior
@@ -35,6 +33,9 @@ use std::thread;
 /// that this implies that the sum of weights should equal one. In some cases, alternative
 /// sums (e.g. zero) are more appropriate, and as such normalization should not be applied
 /// in these cases.
+///
+/// With the `--compute=gpu` flag, in builds with the `gpu` feature, the convolution is
+/// performed on the GPU, in single precision, when a suitable GPU is available.
 pub struct UserDefinedWeightsFilter {
     name: String,
     description: String,
@@ -80,7 +81,7 @@ impl UserDefinedWeightsFilter {
 
         parameters.push(ToolParameter{
             name: "Kernel Center".to_owned(), 
-            flags: vec!["--center".to_owned()], 
+            flags: vec!["--center".to_owned(), "--centre".to_owned()], 
             description: "Kernel center cell; options include 'center', 'upper-left', 'upper-right', 'lower-left', 'lower-right'".to_owned(),
             parameter_type: ParameterType::OptionList(vec!["center".to_owned(), "upper-left".to_owned(), "upper-right".to_owned(), "lower-left".to_owned(), "lower-right".to_owned()]),
             default_value: Some("center".to_owned()),
@@ -154,52 +155,17 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut weights_file = String::new();
-        let mut kernel_center = "center".to_string();
-        let mut normalize = false;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-weights" {
-                weights_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-center" || flag_val == "-centre" {
-                kernel_center = vec[1].to_string().to_lowercase();
-            } else if flag_val == "-normalize" {
-                normalize = true;
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut weights_file = parsed.get_string("weights").unwrap_or_default();
+        let kernel_center = parsed
+            .get_string("center")
+            .unwrap_or("center".to_string())
+            .to_lowercase();
+        let normalize = parsed.get_bool("normalize");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -212,27 +178,19 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !weights_file.contains(&sep) && !weights_file.contains("/") {
-            weights_file = format!("{}{}", working_directory, weights_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        weights_file = resolve_input_path(&weights_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         // read in the filter weights
         let f = File::open(weights_file.clone())?;
         let f = BufReader::new(f);
         let mut weight: f64;
-        let mut num_pixels_in_filter = 0;
         let mut weights = vec![];
         let mut kernel_rows = 0;
         let mut kernel_columns = 0;
         for line in f.lines() {
+            reporter.check_cancelled()?;
             let line_unwrapped = line.unwrap();
             let mut line_split = line_unwrapped.split(",");
             let mut vec = line_split.collect::<Vec<&str>>();
@@ -247,13 +205,15 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
             for i in 0..vec.len() {
                 weight = vec[i].trim().parse::<f64>().unwrap();
                 weights.push(weight);
-                num_pixels_in_filter += 1;
             }
         }
 
-        // calculate the filter offsets
-        let mut d_x: Vec<isize> = Vec::with_capacity(num_pixels_in_filter);
-        let mut d_y: Vec<isize> = Vec::with_capacity(num_pixels_in_filter);
+        if weights.len() != kernel_rows * kernel_columns {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Each row of the filter kernel file must contain the same number of weights.",
+            ));
+        }
 
         let (kernel_center_x, kernel_center_y) = match &kernel_center as &str {
             "upper-left" => (0isize, 0isize),
@@ -280,17 +240,6 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
             }
         };
 
-        // fill the filter d_x and d_y values and the distance-weights
-        for row in 0..kernel_rows {
-            for col in 0..kernel_columns {
-                d_x.push(col as isize - kernel_center_x);
-                d_y.push(row as isize - kernel_center_y);
-            }
-        }
-
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
-
         if verbose {
             println!("Reading data...")
         };
@@ -307,112 +256,48 @@ impl WhiteboxTool for UserDefinedWeightsFilter {
             false
         };
 
-        let rows = input.configs.rows as isize;
         let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
-        let d_x = Arc::new(d_x);
-        let d_y = Arc::new(d_y);
-        let weights = Arc::new(weights);
-        let mut num_procs = num_cpus::get() as isize;
+        let kernel = Arc::new(Kernel::new(
+            weights,
+            kernel_rows,
+            kernel_columns,
+            kernel_center_y,
+            kernel_center_x,
+        ));
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let d_x = d_x.clone();
-            let d_y = d_y.clone();
-            let weights = weights.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let input_fn: Box<dyn Fn(isize, isize) -> f64> = if !is_rgb_image {
-                    Box::new(|row: isize, col: isize| -> f64 { input.get_value(row, col) })
-                } else {
-                    Box::new(|row: isize, col: isize| -> f64 {
-                        let value = input.get_value(row, col);
-                        if value != nodata {
-                            return value2i(value);
-                        }
-                        nodata
-                    })
-                };
-
-                let output_fn: Box<dyn Fn(isize, isize, f64) -> f64> = if !is_rgb_image {
-                    // simply return the value.
-                    Box::new(|_: isize, _: isize, value: f64| -> f64 { value })
-                } else {
-                    // convert it back into an rgb value, using the modified intensity value.
-                    Box::new(|row: isize, col: isize, value: f64| -> f64 {
-                        if value != nodata {
-                            let (h, s, _) = value2hsi(input.get_value(row, col));
-                            return hsi2value(h, s, value);
-                        }
-                        nodata
-                    })
-                };
-
-                let (mut sum_weights, mut z_final): (f64, f64);
-                let mut z: f64;
-                let mut zn: f64;
-                let (mut x, mut y): (isize, isize);
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    let mut data = vec![nodata; columns as usize];
-                    if normalize {
-                        for col in 0..columns {
-                            z = input_fn(row, col);
-                            if z != nodata {
-                                sum_weights = 0.0;
-                                z_final = 0.0;
-                                for a in 0..num_pixels_in_filter {
-                                    x = col + d_x[a];
-                                    y = row + d_y[a];
-                                    zn = input_fn(y, x);
-                                    if zn != nodata {
-                                        sum_weights += weights[a];
-                                        z_final += weights[a] * zn;
-                                    }
-                                }
-                                if sum_weights > 0f64 {
-                                    data[col as usize] = output_fn(row, col, z_final / sum_weights);
-                                }
-                            }
-                        }
-                    } else {
-                        for col in 0..columns {
-                            z = input_fn(row, col);
-                            if z != nodata {
-                                z_final = 0.0;
-                                for a in 0..num_pixels_in_filter {
-                                    x = col + d_x[a];
-                                    y = row + d_y[a];
-                                    zn = input_fn(y, x);
-                                    if zn != nodata {
-                                        z_final += weights[a] * zn;
-                                    }
-                                }
-                                data[col as usize] = output_fn(row, col, z_final);
-                            }
-                        }
-                    }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
-                    tx1.send((row, data)).unwrap();
-                }
-            });
-        }
+        let conv_input = Arc::new(ConvolutionInput::new(
+            &input,
+            if is_rgb_image { Some(value2i) } else { None },
+        ));
+        let filtered = convolve(
+            &conv_input,
+            &kernel,
+            if normalize {
+                NodataMode::Normalize
+            } else {
+                NodataMode::Ignore
+            },
+            nodata,
+            num_procs,
+            verbose,
+            reporter,
+        )?;
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
-        for row in 0..rows {
-            let data = rx.recv().expect("Error receiving data from thread.");
-            output.set_row_data(data.0, data.1);
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
+        for (row, mut data) in filtered.into_iter().enumerate() {
+            if is_rgb_image {
+                // convert it back into an rgb value, using the modified intensity value.
+                for col in 0..columns {
+                    if !is_nodata(data[col as usize], nodata) {
+                        let (h, s, _) = value2hsi(input.get_value(row as isize, col));
+                        data[col as usize] = hsi2value(h, s, data[col as usize]);
+                    }
                 }
             }
+            output.set_row_data(row as isize, data);
         }
 
         let elapsed_time = get_formatted_elapsed_time(start);
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// Jensen (2015) describes write function memory (WFM) insertion as a simple yet effective method of visualizing
 /// land-cover change between two or three dates. WFM insertion may be used to qualitatively inspect change in any
@@ -158,53 +157,17 @@ impl WhiteboxTool for WriteFunctionMemoryInsertion {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input1_file = String::new();
-        let mut input2_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input1_file = parsed.get_string("input1").unwrap_or_default();
+        let mut input2_file = parsed.get_string("input2").unwrap_or_default();
         let mut input3_file = String::new();
         let mut input3_used = false;
-        let mut output_file = String::new();
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "--i1" || vec[0].to_lowercase() == "--input1" {
-                if keyval {
-                    input1_file = vec[1].to_string();
-                } else {
-                    input1_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "--i2" || vec[0].to_lowercase() == "--input2" {
-                if keyval {
-                    input2_file = vec[1].to_string();
-                } else {
-                    input2_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "--i3" || vec[0].to_lowercase() == "--input3" {
-                if keyval {
-                    input3_file = vec[1].to_string();
-                } else {
-                    input3_file = args[i + 1].to_string();
-                }
-                input3_used = true;
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        if let Some(value) = parsed.get_string("input3") {
+            input3_file = value;
+            input3_used = true;
         }
 
         if verbose {
@@ -218,25 +181,15 @@ impl WhiteboxTool for WriteFunctionMemoryInsertion {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input1_file.contains(&sep) && !input1_file.contains("/") {
-            input1_file = format!("{}{}", working_directory, input1_file);
-        }
-        if !input2_file.contains(&sep) && !input2_file.contains("/") {
-            input2_file = format!("{}{}", working_directory, input2_file);
-        }
+        input1_file = resolve_input_path(&input1_file, working_directory)?;
+        input2_file = resolve_input_path(&input2_file, working_directory)?;
         if input3_used {
-            if !input3_file.contains(&sep) && !input3_file.contains("/") {
-                input3_file = format!("{}{}", working_directory, input3_file);
-            }
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
+            input3_file = resolve_input_path(&input3_file, working_directory)?;
         }
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -255,19 +208,15 @@ impl WhiteboxTool for WriteFunctionMemoryInsertion {
         if input_r.configs.rows != input_g.configs.rows
             || input_r.configs.columns != input_g.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input_r, &input_g, verbose);
         if input_r.configs.rows != input_b.configs.rows
             || input_r.configs.columns != input_b.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input_r, &input_b, verbose);
 
         let rows = input_r.configs.rows as isize;
         let columns = input_r.configs.columns as isize;
@@ -281,19 +230,15 @@ impl WhiteboxTool for WriteFunctionMemoryInsertion {
         let green_range = input_g.configs.display_max - green_min;
         let blue_range = input_b.configs.display_max - blue_min;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input_r = input_r.clone();
             let input_g = input_g.clone();
             let input_b = input_b.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut red_val: f64;
                 let mut green_val: f64;
                 let mut blue_val: f64;
@@ -305,7 +250,7 @@ impl WhiteboxTool for WriteFunctionMemoryInsertion {
                         red_val = input_r[(row, col)];
                         green_val = input_g[(row, col)];
                         blue_val = input_b[(row, col)];
-                        if red_val != nodata_r && green_val != nodata_g && blue_val != nodata_b {
+                        if !is_nodata(red_val, nodata_r) && !is_nodata(green_val, nodata_g) && !is_nodata(blue_val, nodata_b) {
                             red_val = (red_val - red_min) / red_range * 255f64;
                             if red_val < 0f64 {
                                 red_val = 0f64;
@@ -335,7 +280,9 @@ impl WhiteboxTool for WriteFunctionMemoryInsertion {
                             data[col as usize] = (alpha_mask | (b << 16) | (g << 8) | r) as f64;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -346,10 +293,11 @@ impl WhiteboxTool for WriteFunctionMemoryInsertion {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,343 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 17/10/2026
+License: MIT
+
+NOTES: With the --json flag, the command-line program reports a tool run as a series of JSON
+events, one object per line on the standard output, in place of the usual text, so that programs
+that orchestrate tool runs can parse their results reliably. The events are:
+
+  {"event":"start","tool":...,"version":...,"working_directory":...,"args":[...],"timestamp":...}
+  {"event":"metadata","tool":...,"toolbox":...,"description":...,"parameters":{...}}
+  {"event":"progress","label":"Progress","progress":45}
+  {"event":"message","message":...}
+  {"event":"end","status":"succeeded"|"failed","elapsed_seconds":...,"outputs":[...],"error":...,
+   "error_kind":...,"exit_code":...}
+
+The tool is run within the program, as the server runs them, and its progress is sent as events by
+the `ProgressReporter` that it is given, rather than being read back from its text. On Unix-like
+systems, the tool is run in verbose mode and the standard output of the process is redirected into
+a pipe for the duration of the run, such that the text that the tool prints, including that of its
+worker threads, becomes message events (less the welcome banner) while the events themselves are
+written to the original standard output. Elsewhere the tool is run quietly, such that there are no
+progress or message events. A tool that panics is still reported by an end event. The end event of
+a successful run lists the output files that exist after it, and that of a failed run gives the
+cause of the failure (see the errors module of whitebox_common) as its error_kind, e.g.
+"grid_mismatch", along with the exit code of the program.
+*/
+
+use super::{ProgressReporter, ToolManager};
+use chrono::prelude::*;
+use serde_json::{json, Map, Value};
+use std::io::{self, Error, Write};
+use std::panic;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use whitebox_common::errors::ErrorCause;
+
+const VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSION");
+
+/// Writes events, one per line, to the program's standard output.
+struct EventWriter {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl EventWriter {
+    fn emit(&self, event: Value) {
+        if let Ok(mut out) = self.out.lock() {
+            let _ = writeln!(out, "{}", event);
+            let _ = out.flush();
+        }
+    }
+}
+
+/// Sends the progress of a tool as progress events.
+struct EventProgress {
+    events: Arc<EventWriter>,
+}
+
+impl ProgressReporter for EventProgress {
+    fn progress(&self, label: &str, percent: usize) {
+        self.events.emit(json!({
+            "event": "progress",
+            "label": label,
+            "progress": percent,
+        }));
+    }
+}
+
+/// Translates the lines of text printed by a tool into message events, leaving out the welcome
+/// banner that tools print before anything else when they are run in verbose mode.
+struct TextEvents {
+    tool_name: String,
+    in_banner: bool,
+}
+
+impl TextEvents {
+    fn new(tool_name: &str) -> TextEvents {
+        TextEvents {
+            tool_name: tool_name.to_string(),
+            in_banner: true,
+        }
+    }
+
+    fn event(&mut self, line: &str) -> Option<Value> {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            return None;
+        }
+        if self.in_banner {
+            if self.is_banner_line(line) {
+                return None;
+            }
+            self.in_banner = false;
+        }
+        Some(json!({ "event": "message", "message": line }))
+    }
+
+    fn is_banner_line(&self, line: &str) -> bool {
+        line.chars().all(|c| c == '*')
+            || (line.ends_with('*')
+                && (line.starts_with(&format!("* Welcome to {} ", self.tool_name))
+                    || line.starts_with("* Powered by WhiteboxTools ")
+                    || line.starts_with("* www.whiteboxgeo.com ")))
+    }
+}
+
+/// Runs a tool and prints its events.
+pub(crate) fn run_json_events(tm: &ToolManager, tool_name: &str, args: &[String]) -> Result<(), Error> {
+    let tool = tm.get_tool(tool_name).ok_or_else(|| {
+        ErrorCause::InvalidParameter.error(format!("Unrecognized tool name {}.", tool_name))
+    })?;
+    let (events, capture) = match capture::redirect_stdout() {
+        Ok((stdout, capture)) => (EventWriter { out: Mutex::new(Box::new(stdout)) }, Some(capture)),
+        Err(_) => (EventWriter { out: Mutex::new(Box::new(io::stdout())) }, None),
+    };
+    let events = Arc::new(events);
+    events.emit(json!({
+        "event": "start",
+        "tool": tool.get_tool_name(),
+        "version": VERSION.unwrap_or("unknown"),
+        "working_directory": tm.working_dir,
+        "args": args,
+        "timestamp": Local::now().to_rfc3339(),
+    }));
+    let mut parameters = Map::new();
+    for (param, value) in super::parse_tool_args(&tool.get_tool_parameters(), args)? {
+        let flag = param["flags"]
+            .as_array()
+            .and_then(|flags| flags.last())
+            .and_then(|f| f.as_str())
+            .unwrap_or("")
+            .trim_start_matches('-')
+            .to_string();
+        parameters.insert(flag, Value::String(value));
+    }
+    events.emit(json!({
+        "event": "metadata",
+        "tool": tool.get_tool_name(),
+        "toolbox": tool.get_toolbox(),
+        "description": tool.get_tool_description(),
+        "parameters": parameters,
+    }));
+
+    // The tool only prints its text, and reports its progress, in verbose mode, which is only
+    // used when its text can be kept out of the events.
+    let verbose = capture.is_some();
+    let reader = capture.as_ref().map(|c| {
+        let events = events.clone();
+        let mut text = TextEvents::new(&tool.get_tool_name());
+        c.read_lines(move |line| {
+            if let Some(event) = text.event(&line) {
+                events.emit(event);
+            }
+        })
+    });
+    let mut run_tm = ToolManager::new(&tm.working_dir, &verbose)?;
+    if let Some(token) = &tm.cancellation {
+        run_tm.set_cancellation_token(token.clone());
+    }
+    let reporter = EventProgress { events: events.clone() };
+    let start = Instant::now();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        run_tm.run_tool_with_progress(tool_name.to_string(), args.to_vec(), &reporter)
+    }));
+    let elapsed_seconds = start.elapsed().as_secs_f64();
+    if let Some(capture) = capture {
+        capture.restore();
+    }
+    if let Some(reader) = reader {
+        // the remaining text is read once the pipe has been closed
+        let _ = reader.join();
+    }
+
+    let error = match result {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some((ErrorCause::of(&e), e.to_string())),
+        Err(payload) => {
+            // the message of the panic has already been printed to the standard error
+            let message = payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or(String::from("The tool panicked."));
+            Some((ErrorCause::Other, message))
+        }
+    };
+    let outputs = if error.is_none() {
+        tm.existing_outputs(tool_name, args)
+    } else {
+        vec![]
+    };
+    let mut end = json!({
+        "event": "end",
+        "status": if error.is_none() { "succeeded" } else { "failed" },
+        "elapsed_seconds": elapsed_seconds,
+        "outputs": outputs,
+        "error": Value::Null,
+    });
+    if let Some((cause, message)) = &error {
+        end["error"] = json!(message);
+        end["error_kind"] = json!(cause.name());
+        end["exit_code"] = json!(cause.exit_code());
+    }
+    events.emit(end);
+    match error {
+        Some((cause, message)) => Err(cause.error(message)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(unix)]
+mod capture {
+    use std::fs::File;
+    use std::io::{self, BufRead, BufReader, Error, Write};
+    use std::os::unix::io::FromRawFd;
+    use std::thread::{self, JoinHandle};
+
+    extern "C" {
+        fn pipe(fds: *mut i32) -> i32;
+        fn dup(fd: i32) -> i32;
+        fn dup2(fd: i32, fd2: i32) -> i32;
+        fn close(fd: i32) -> i32;
+    }
+
+    const STDOUT: i32 = 1;
+
+    /// The standard output of the process while it is redirected into a pipe.
+    pub struct StdoutCapture {
+        saved: i32,
+        read_end: i32,
+    }
+
+    /// Redirects the standard output of the process into a pipe, returning the original standard
+    /// output along with the capture.
+    pub fn redirect_stdout() -> Result<(File, StdoutCapture), Error> {
+        io::stdout().flush()?;
+        let mut fds = [0i32; 2];
+        unsafe {
+            if pipe(fds.as_mut_ptr()) != 0 {
+                return Err(Error::last_os_error());
+            }
+            let saved = dup(STDOUT);
+            let original = dup(STDOUT);
+            if saved < 0 || original < 0 || dup2(fds[1], STDOUT) < 0 {
+                let e = Error::last_os_error();
+                for fd in [fds[0], fds[1], saved, original] {
+                    if fd >= 0 {
+                        close(fd);
+                    }
+                }
+                return Err(e);
+            }
+            // the pipe is now only written through the standard output
+            close(fds[1]);
+            Ok((
+                File::from_raw_fd(original),
+                StdoutCapture {
+                    saved,
+                    read_end: fds[0],
+                },
+            ))
+        }
+    }
+
+    impl StdoutCapture {
+        /// Passes each line written to the standard output to `on_line`, in a thread that ends
+        /// once the standard output has been restored.
+        pub fn read_lines<F: FnMut(String) + Send + 'static>(&self, mut on_line: F) -> JoinHandle<()> {
+            let read_end = unsafe { dup(self.read_end) };
+            thread::spawn(move || {
+                if read_end < 0 {
+                    return;
+                }
+                let reader = BufReader::new(unsafe { File::from_raw_fd(read_end) });
+                for line in reader.split(b'\n').map_while(Result::ok) {
+                    on_line(String::from_utf8_lossy(&line).to_string());
+                }
+            })
+        }
+
+        /// Restores the original standard output, closing the pipe.
+        pub fn restore(self) {
+            let _ = io::stdout().flush();
+            unsafe {
+                dup2(self.saved, STDOUT);
+                close(self.saved);
+                close(self.read_end);
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod capture {
+    use std::fs::File;
+    use std::io::{Error, ErrorKind};
+    use std::thread::JoinHandle;
+
+    pub struct StdoutCapture;
+
+    pub fn redirect_stdout() -> Result<(File, StdoutCapture), Error> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "The standard output can't be captured on this platform.",
+        ))
+    }
+
+    impl StdoutCapture {
+        pub fn read_lines<F: FnMut(String) + Send + 'static>(&self, _on_line: F) -> JoinHandle<()> {
+            unreachable!()
+        }
+
+        pub fn restore(self) {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TextEvents;
+
+    #[test]
+    fn test_text_events() {
+        let mut text = TextEvents::new("Slope");
+        for line in [
+            "*****************************",
+            "* Welcome to Slope         *",
+            "* Powered by WhiteboxTools *",
+            "* www.whiteboxgeo.com      *",
+            "*****************************",
+            "",
+        ] {
+            assert!(text.event(line).is_none(), "{}", line);
+        }
+        // once the banner has passed, every line is a message, however it starts
+        for line in ["Reading data...", "* 3 cells were filled", "Warnings are disabled", "****"] {
+            let event = text.event(line).unwrap();
+            assert_eq!(event["event"], "message");
+            assert_eq!(event["message"], line);
+        }
+    }
+}
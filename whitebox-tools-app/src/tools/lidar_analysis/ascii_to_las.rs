@@ -17,12 +17,13 @@ use std::io::{Error, ErrorKind};
 use std::path;
 extern crate byteorder;
 use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+use whitebox_common::errors::ErrorCause;
 
 /// This tool can be used to convert one or more ASCII files, containing LiDAR point data, into LAS files. The user must
 /// specify the name(s) of the input ASCII file(s) (`--inputs`). Each input file will have a correspondingly named
 /// output file with a `.las` file extension. The output point data, each on a separate line, will take the format:
 ///
-/// ```
+/// ```text
 /// x,y,z,intensity,class,return,num_returns"
 /// ```
 ///
@@ -44,7 +45,7 @@ use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
 /// The `x`, `y`, and `z` patterns must always be specified. If the `rn` pattern is used, the `nr` pattern must
 /// also be specified. Examples of valid pattern string include:
 ///
-/// ```
+/// ```text
 /// 'x,y,z,i'
 /// 'x,y,z,i,rn,nr'
 /// 'x,y,z,i,c,rn,nr,sa'
@@ -75,7 +76,7 @@ impl AsciiToLas {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input LiDAR point ASCII Files (.csv)".to_owned(),
-            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--inputs".to_owned()],
             description: "Input LiDAR  ASCII files (.csv).".to_owned(),
             parameter_type: ParameterType::FileList(ParameterFileType::Csv),
             default_value: None,
@@ -113,7 +114,7 @@ impl AsciiToLas {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=\"file1.las, file2.las, file3.las\" -o=outfile.las\" --proj=2150", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=\"file1.las, file2.las, file3.las\" --pattern=\"x,y,z,i,c,rn,nr,sa\" --proj=2150", short_exe, name).replace("*", &sep);
 
         AsciiToLas {
             name: name,
@@ -165,60 +166,31 @@ impl WhiteboxTool for AsciiToLas {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files: String = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files: String = parsed.get_string("inputs").unwrap_or_default();
         let mut pattern_string = String::new();
         let mut proj_string = String::new();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_string("pattern") {
+            pattern_string = value.to_string().to_lowercase();
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" || flag_val == "-input" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-pattern" {
-                pattern_string = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-            } else if flag_val == "-proj" {
-                proj_string = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-
-                if proj_string.parse::<u16>().is_ok() {
-                    proj_string = esri_wkt_from_epsg(
-                        proj_string
-                            .trim()
-                            .parse::<u16>()
-                            .expect("Error parsing EPSG code."),
-                    );
-                    if proj_string.to_lowercase() == "unknown epsg code" {
-                        return Err(Error::new(
-                            ErrorKind::InvalidInput,
-                            "Error: The specified EPSG is unrecognized or unsupported. Please report this error.",
-                        ));
-                    }
+        if let Some(value) = parsed.get_string("proj") {
+            proj_string = value.to_string().to_lowercase();
+
+            if proj_string.parse::<u16>().is_ok() {
+                proj_string = esri_wkt_from_epsg(
+                    proj_string
+                        .trim()
+                        .parse::<u16>()
+                        .expect("Error parsing EPSG code."),
+                );
+                if proj_string.to_lowercase() == "unknown epsg code" {
+                    return Err(ErrorCause::UnsupportedFormat.error(
+                        "Error: The specified EPSG is unrecognized or unsupported. Please report this error.",
+                    ));
                 }
             }
         }
@@ -234,8 +206,6 @@ impl WhiteboxTool for AsciiToLas {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = std::path::MAIN_SEPARATOR;
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -334,9 +304,7 @@ impl WhiteboxTool for AsciiToLas {
         for value in files_vec {
             if !value.trim().is_empty() {
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
 
                 // Initialize the output LAS file
                 let file_extension = get_file_extension(&input_file);
@@ -577,10 +545,11 @@ impl WhiteboxTool for AsciiToLas {
                     Err(e) => println!("error while writing: {:?}", e),
                 };
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (i + 1) as f64 / num_files as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
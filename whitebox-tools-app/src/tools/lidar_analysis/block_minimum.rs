@@ -9,7 +9,6 @@ License: MIT
 use whitebox_lidar::*;
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs;
@@ -17,7 +16,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 pub struct LidarBlockMinimum {
     name: String,
@@ -137,61 +136,15 @@ impl WhiteboxTool for LidarBlockMinimum {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut grid_res: f64 = 1.0;
-        let mut palette = "default".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
+        let palette = parsed.get_string("palette").unwrap_or("default".to_string());
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-palette" {
-                palette = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
         let start = Instant::now();
 
@@ -273,9 +226,7 @@ impl WhiteboxTool for LidarBlockMinimum {
                     .replace(".LAZ", ".tif")
                     .replace(".zlidar", ".tif");
             }
-            if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
             outputs.push(output_file);
         }
 
@@ -303,12 +254,8 @@ impl WhiteboxTool for LidarBlockMinimum {
                 );
             }
 
-            if !input_file.contains(path::MAIN_SEPARATOR) {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
-            if !output_file.contains(path::MAIN_SEPARATOR) {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            input_file = resolve_input_path(&input_file, working_directory)?;
+            output_file = resolve_path(&output_file, working_directory);
 
             if verbose && inputs.len() == 1 {
                 println!("reading input LiDAR file...");
@@ -357,12 +304,12 @@ impl WhiteboxTool for LidarBlockMinimum {
             let mut output = Raster::initialize_using_config(&output_file, &configs);
 
             let input = Arc::new(input); // wrap input in an Arc
-            let num_procs = num_cpus::get();
+            let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut col: isize;
                     let mut row: isize;
                     for i in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
@@ -372,7 +319,9 @@ impl WhiteboxTool for LidarBlockMinimum {
                             .floor()) as isize;
                         row = (((rows - 1) as f64 * (north - half_grid_res - p.y) / ns_range)
                             .floor()) as isize;
-                        tx.send((row, col, p.z)).unwrap();
+                        if tx.send((row, col, p.z)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -387,13 +336,14 @@ impl WhiteboxTool for LidarBlockMinimum {
                 row = data.0;
                 col = data.1;
                 z = data.2;
-                if output[(row, col)] == nodata || z < output[(row, col)] {
+                if is_nodata(output[(row, col)], nodata) || z < output[(row, col)] {
                     output.set_value(row, col, z);
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -11,12 +11,11 @@ use whitebox_lidar::*;
 use whitebox_common::structures::{BoundingBox, Point2D, Point3D};
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
-use num_cpus;
 use std::env;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::{mpsc, Arc};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to assign the building class (classification value 6) to all points within an
 /// input LiDAR point cloud (`--input`) that are contained within the polygons of an input buildings
@@ -59,7 +58,7 @@ impl ClassifyBuildingsInLidar {
 
         parameters.push(ToolParameter {
             name: "Input Building Polygon File".to_owned(),
-            flags: vec!["--buildings".to_owned()],
+            flags: vec!["--building".to_owned(), "--buildings".to_owned()],
             description: "Input vector polygons file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
                 VectorGeometryType::Polygon,
@@ -90,7 +89,7 @@ impl ClassifyBuildingsInLidar {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='data.las' --polygons='lakes.shp' -o='output.las'", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='data.las' --buildings='buildings.shp' -o='output.las'", short_exe, name).replace("*", &sep);
 
         ClassifyBuildingsInLidar {
             name: name,
@@ -135,47 +134,12 @@ impl WhiteboxTool for ClassifyBuildingsInLidar {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut polygons_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-building" || flag_val == "-buildings" {
-                polygons_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut polygons_file = parsed.get_string("buildings").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -188,20 +152,12 @@ impl WhiteboxTool for ClassifyBuildingsInLidar {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !polygons_file.contains(&sep) && !polygons_file.contains("/") {
-            polygons_file = format!("{}{}", working_directory, polygons_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        polygons_file = resolve_input_path(&polygons_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -236,6 +192,7 @@ impl WhiteboxTool for ClassifyBuildingsInLidar {
         let mut feature_bb;
         let mut record_nums = Vec::with_capacity(num_records);
         for record_num in 0..polygons.num_records {
+            reporter.check_cancelled()?;
             let record = polygons.get_record(record_num);
             feature_bb = BoundingBox::new(record.x_min, record.x_max, record.y_min, record.y_max);
             if feature_bb.overlaps(lidar_bb) {
@@ -251,7 +208,7 @@ impl WhiteboxTool for ClassifyBuildingsInLidar {
         let n_points = input.header.number_of_points as usize;
         let num_points: f64 = (input.header.number_of_points - 1) as f64; // used for progress calculation only
 
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let input = Arc::new(input);
         let polygons = Arc::new(polygons);
         let record_nums = Arc::new(record_nums);
@@ -263,7 +220,7 @@ impl WhiteboxTool for ClassifyBuildingsInLidar {
             let record_nums = record_nums.clone();
             let bb = bb.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 // let mut p: PointData;
                 let mut p: Point3D;
                 let mut record_num: usize;
@@ -320,13 +277,9 @@ impl WhiteboxTool for ClassifyBuildingsInLidar {
                             }
                         }
                     }
-                    match tx.send((point_in_poly, point_num)) {
-                        Ok(_) => {} // do nothing
-                        Err(_) => panic!(
-                            "Error performing clipping operation on point num. {}",
-                            point_num
-                        ),
-                    };
+                    if tx.send((point_in_poly, point_num)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -470,10 +423,11 @@ impl WhiteboxTool for ClassifyBuildingsInLidar {
                 }
                 output.add_point_record(pr2);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -24,7 +24,7 @@ use whitebox_common::structures::Point3D;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to flag points within an input LiDAR file (`--input`) that overlap with other 
@@ -112,7 +112,7 @@ impl ClassifyOverlapPoints {
                 "The size of the square area used to evaluate nearby points in the LiDAR data."
                     .to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("2.0".to_owned()),
+            default_value: Some("1.0".to_owned()),
             optional: true,
         });
 
@@ -210,105 +210,52 @@ impl WhiteboxTool for ClassifyOverlapPoints {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut grid_res: f64 = 1.0;
-        let mut filter = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
+        let filter = parsed.get_bool("filter");
         let mut based_on_scan_angle = true;
         let mut based_on_min_pt_src_id = false;
         let mut based_on_min_time = false;
         let mut based_on_all_overlapping_pts = false;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-c" || flag_val == "-criterion" {
-                let criterion = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-
-                if criterion.contains("scan") {
-                    based_on_scan_angle = true;
-                    based_on_min_pt_src_id = false;
-                    based_on_min_time = false;
-                    based_on_all_overlapping_pts = false;
-                } else if criterion.contains("min point source id") || criterion.contains("min pt_src_id") {
-                    based_on_scan_angle = false;
-                    based_on_min_pt_src_id = true;
-                    based_on_min_time = false;
-                    based_on_all_overlapping_pts = false;
-                } else if criterion.contains("time") {
-                    based_on_scan_angle = false;
-                    based_on_min_pt_src_id = false;
-                    based_on_min_time = true;
-                    based_on_all_overlapping_pts = false;
-                } else { // multiple point source IDs -- i.e. all points in overlap areas
-                    based_on_scan_angle = false;
-                    based_on_min_pt_src_id = false;
-                    based_on_min_time = false;
-                    based_on_all_overlapping_pts = true;
-                }
-            } else if flag_val == "-filter" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    filter = true;
-                }
+        if let Some(value) = parsed.get_string("criterion") {
+            let criterion = value.to_string().to_lowercase();
+
+            if criterion.contains("scan") {
+                based_on_scan_angle = true;
+                based_on_min_pt_src_id = false;
+                based_on_min_time = false;
+                based_on_all_overlapping_pts = false;
+            } else if criterion.contains("min point source id") || criterion.contains("min pt_src_id") {
+                based_on_scan_angle = false;
+                based_on_min_pt_src_id = true;
+                based_on_min_time = false;
+                based_on_all_overlapping_pts = false;
+            } else if criterion.contains("time") {
+                based_on_scan_angle = false;
+                based_on_min_pt_src_id = false;
+                based_on_min_time = true;
+                based_on_all_overlapping_pts = false;
+            } else { // multiple point source IDs -- i.e. all points in overlap areas
+                based_on_scan_angle = false;
+                based_on_min_pt_src_id = false;
+                based_on_min_time = false;
+                based_on_all_overlapping_pts = true;
             }
         }
 
         let start = Instant::now();
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -340,6 +287,7 @@ impl WhiteboxTool for ClassifyOverlapPoints {
         let mut points: Vec<TreeItem> = Vec::with_capacity(n_points);
         let mut p: Point3D;
         for i in 0..n_points {
+            reporter.check_cancelled()?;
             if !input[i].withheld() {
                 p = input.get_transformed_coords(i);
                 points.push( TreeItem { point: [p.x, p.y ], id: i } );
@@ -446,7 +394,6 @@ impl WhiteboxTool for ClassifyOverlapPoints {
                         }
                     }
                 }
-                
 
                 // let ret = frs.search(x, y);
                 // if ret.len() > 0 {
@@ -501,10 +448,11 @@ impl WhiteboxTool for ClassifyOverlapPoints {
                 //     }
                 // }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -521,10 +469,11 @@ impl WhiteboxTool for ClassifyOverlapPoints {
                 if !overlapping[i] {
                     output.add_point_record(input.get_record(i));
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as usize;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress);
                         old_progress = progress;
                     }
                 }
@@ -665,10 +614,11 @@ impl WhiteboxTool for ClassifyOverlapPoints {
                     }
                     output.add_point_record(pr2);
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as usize;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress);
                         old_progress = progress;
                     }
                 }
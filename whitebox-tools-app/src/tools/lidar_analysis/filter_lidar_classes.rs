@@ -10,7 +10,7 @@ use whitebox_lidar::*;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 
 /// This tool can be used to remove points within a LAS LiDAR file that possess certain
@@ -158,74 +158,43 @@ impl WhiteboxTool for FilterLidarClasses {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
         let mut include_class_vals = vec![true; 256];
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_string("exclude_cls") {
+            let exclude_cls_str = value.to_string();
+            let mut cmd = exclude_cls_str.split(",");
+            let mut vec = cmd.collect::<Vec<&str>>();
+            if vec.len() == 1 {
+                cmd = exclude_cls_str.split(";");
+                vec = cmd.collect::<Vec<&str>>();
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-exclude_cls" {
-                let exclude_cls_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                let mut cmd = exclude_cls_str.split(",");
-                let mut vec = cmd.collect::<Vec<&str>>();
-                if vec.len() == 1 {
-                    cmd = exclude_cls_str.split(";");
-                    vec = cmd.collect::<Vec<&str>>();
-                }
-                for value in vec {
-                    if !value.trim().is_empty() {
-                        if value.contains("-") {
-                            cmd = value.split("-");
-                            vec = cmd.collect::<Vec<&str>>();
-                            let c = vec[0].trim().parse::<usize>().unwrap();
-                            let d = vec[1].trim().parse::<usize>().unwrap();
-                            for e in c..=d {
-                                include_class_vals[e] = false;
-                            }
-                        } else if value.contains("...") {
-                            cmd = value.split("...");
-                            vec = cmd.collect::<Vec<&str>>();
-                            let c = vec[0].trim().parse::<usize>().unwrap();
-                            let d = vec[1].trim().parse::<usize>().unwrap();
-                            for e in c..=d {
-                                include_class_vals[e] = false;
-                            }
-                        } else {
-                            let c = value.trim().parse::<usize>().unwrap();
-                            include_class_vals[c] = false;
+            for value in vec {
+                if !value.trim().is_empty() {
+                    if value.contains("-") {
+                        cmd = value.split("-");
+                        vec = cmd.collect::<Vec<&str>>();
+                        let c = vec[0].trim().parse::<usize>().unwrap();
+                        let d = vec[1].trim().parse::<usize>().unwrap();
+                        for e in c..=d {
+                            include_class_vals[e] = false;
                         }
+                    } else if value.contains("...") {
+                        cmd = value.split("...");
+                        vec = cmd.collect::<Vec<&str>>();
+                        let c = vec[0].trim().parse::<usize>().unwrap();
+                        let d = vec[1].trim().parse::<usize>().unwrap();
+                        for e in c..=d {
+                            include_class_vals[e] = false;
+                        }
+                    } else {
+                        let c = value.trim().parse::<usize>().unwrap();
+                        include_class_vals[c] = false;
                     }
                 }
             }
@@ -242,13 +211,8 @@ impl WhiteboxTool for FilterLidarClasses {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -278,10 +242,11 @@ impl WhiteboxTool for FilterLidarClasses {
             if include_class_vals[input[i].classification() as usize] {
                 output.add_point_record(input.get_record(i));
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -16,14 +16,13 @@ use crate::tools::*;
 use whitebox_common::structures::Point3D;
 use kdtree::distance::squared_euclidean;
 use kdtree::KdTree;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool normalizes an input LiDAR point cloud (`--input`) such that point z-values in the output LAS file
 /// (`--output`) are converted from elevations to heights above the ground, specifically the height above the
@@ -137,41 +136,13 @@ impl WhiteboxTool for HeightAboveGround {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -184,17 +155,12 @@ impl WhiteboxTool for HeightAboveGround {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
         let mut progress: i32;
         let mut old_progress: i32 = -1;
         let start = Instant::now();
 
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = match LasFile::new(&input_file, "r") {
             Ok(lf) => lf,
@@ -216,10 +182,11 @@ impl WhiteboxTool for HeightAboveGround {
                 tree.add([p1.x, p1.y], i).unwrap();
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Reading points: {}%", progress);
+                    reporter.progress("Reading points", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -234,14 +201,16 @@ impl WhiteboxTool for HeightAboveGround {
         let num_solved_pts = Arc::new(Mutex::new(0usize));
         let tree = Arc::new(tree);
         let input = Arc::new(input); // wrap input in an Arc
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let num_solved_pts = num_solved_pts.clone();
             let tree = tree.clone();
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut pd1: PointData;
                 // let mut p2: PointData;
                 let mut p1: Point3D;
@@ -252,6 +221,9 @@ impl WhiteboxTool for HeightAboveGround {
                 let mut old_progress: i32;
                 let mut progress: i32;
                 for i in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     pd1 = input[i];
                     p1 = input.get_transformed_coords(i);
                     z = if pd1.classification() != 2u8 {
@@ -272,17 +244,21 @@ impl WhiteboxTool for HeightAboveGround {
                         *num_solved_pts += 1;
                         progress = (100.0_f64 * *num_solved_pts as f64 / num_points) as i32;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            if tx.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                return;
+                            }
                         }
                     }
                 }
-                tx.send(residuals).unwrap();
+                if tx.send(WorkerMessage::Data(residuals)).is_err() {
+                    return;
+                }
             });
         }
 
         let mut output = LasFile::initialize_using_file(&output_file, &input);
         for n in 0..num_procs {
-            let residuals = rx.recv().expect("Error receiving data from thread.");
+            let residuals = recv_data(&rx, reporter).expect("Error receiving data from thread.");
             for (i, z) in residuals {
                 let pr = input.get_record(i);
                 let pr2: LidarPointRecord;
@@ -414,10 +390,11 @@ impl WhiteboxTool for HeightAboveGround {
                 }
                 output.add_point_record(pr2);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (n + 1) as f64 / num_procs as f64) as i32;
                 if progress != old_progress {
-                    println!("Creating output: {}%", progress);
+                    reporter.progress("Creating output", progress as usize);
                     old_progress = progress;
                 }
             }
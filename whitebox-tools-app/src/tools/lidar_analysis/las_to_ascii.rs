@@ -22,7 +22,7 @@ use std::path;
 /// with each column corresponding to a field in the table and each row a point value. Fields are separated by
 /// commas in the ASCII formatted file. The output point data, each on a separate line, will take the format:
 ///
-/// ```
+/// ```text
 /// X,Y,Z,INTENSITY,CLASS,RETURN,NUM_RETURN,SCAN_ANGLE
 /// ```
 ///
@@ -50,7 +50,7 @@ impl LasToAscii {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input LiDAR Files".to_owned(),
-            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--inputs".to_owned()],
             description: "Input LiDAR files.".to_owned(),
             parameter_type: ParameterType::FileList(ParameterFileType::Lidar),
             default_value: None,
@@ -126,34 +126,12 @@ impl WhiteboxTool for LasToAscii {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files: String = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files: String = parsed.get_string("inputs").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" || flag_val == "-input" {
-                if keyval {
-                    input_files = vec[1].to_string();
-                } else {
-                    input_files = args[i + 1].to_string();
-                }
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -166,8 +144,6 @@ impl WhiteboxTool for LasToAscii {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = std::path::MAIN_SEPARATOR;
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -184,9 +160,7 @@ impl WhiteboxTool for LasToAscii {
         for value in vec {
             if !value.trim().is_empty() {
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
 
                 let input: LasFile = match LasFile::new(&input_file, "r") {
                     Ok(lf) => lf,
@@ -311,13 +285,14 @@ impl WhiteboxTool for LasToAscii {
                     };
                     writer.write_all(s.as_bytes())?;
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * k as f64 / (n_points - 1) as f64) as usize;
                         if progress != old_progress {
                             if num_files > 1 {
-                                println!("Creating file: {} of {}: {}%", i, num_files, progress);
+                                reporter.progress(&format!("Creating file: {} of {}", i, num_files), progress);
                             } else {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress);
                             }
                             old_progress = progress;
                         }
@@ -11,14 +11,13 @@ use crate::tools::*;
 use whitebox_vector::ShapefileGeometry;
 use whitebox_vector::*;
 use whitebox_common::structures::Point2D;
-use num_cpus;
 use std::env;
 use std::fs;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// Converts one or more LAS files into MultipointZ vector Shapefiles. When the input parameter is
 /// not specified, the tool grids all LAS files contained within the working directory.
@@ -127,8 +126,10 @@ impl WhiteboxTool for LasToMultipointShapefile {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
 
         // read the arguments
         if args.len() == 0 && working_directory.is_empty() {
@@ -137,24 +138,6 @@ impl WhiteboxTool for LasToMultipointShapefile {
                 "Tool run with no parameters.",
             ));
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -210,27 +193,21 @@ impl WhiteboxTool for LasToMultipointShapefile {
                 ));
             }
         } else {
-            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_input_path(&input_file, working_directory)?;
             inputs.push(input_file.clone());
         }
 
         let num_tiles = inputs.len();
         let tile_list = Arc::new(Mutex::new(0..num_tiles));
         let inputs = Arc::new(inputs);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = channel();
         for _ in 0..num_procs {
             let inputs = inputs.clone();
             let tile_list = tile_list.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut tile = 0;
                 while tile < num_tiles {
                     // Get the next tile up for processing
@@ -314,7 +291,9 @@ impl WhiteboxTool for LasToMultipointShapefile {
                         ),
                     };
                     // send the data to the main thread to be output
-                    tx.send(ret_val).unwrap();
+                    if tx.send(ret_val).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -328,10 +307,11 @@ impl WhiteboxTool for LasToMultipointShapefile {
                 }
                 Err(val) => println!("Error: {:?}", val),
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tile as f64 / (num_tiles - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress ({} of {}): {}%", (tile + 1), num_tiles, progress);
+                    reporter.progress(&format!("Progress ({} of {})", (tile + 1), num_tiles), progress);
                     old_progress = progress;
                 }
             }
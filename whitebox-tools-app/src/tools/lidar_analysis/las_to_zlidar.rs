@@ -12,7 +12,8 @@ use std;
 use std::io::{Error, ErrorKind};
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::{env, fs, path, thread};
+use std::{env, fs, path};
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to convert one or more LAS files into the
 /// [*zLidar*](https://jblindsay.github.io/zLidar_spec/intro.html) compressed
@@ -44,7 +45,7 @@ impl LasToZlidar {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input LAS Files".to_owned(),
-            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--inputs".to_owned()],
             description: "Input LAS files.".to_owned(),
             parameter_type: ParameterType::FileList(ParameterFileType::Lidar),
             default_value: None,
@@ -63,7 +64,7 @@ impl LasToZlidar {
 
         parameters.push(ToolParameter{
             name: "Compression Method".to_owned(), 
-            flags: vec!["--compress".to_owned()], 
+            flags: vec!["--compression".to_owned(), "--compress".to_owned()], 
             description: "Compression method, including 'brotli' and 'deflate'.".to_owned(),
             parameter_type: ParameterType::OptionList(
                 vec![
@@ -153,60 +154,17 @@ impl WhiteboxTool for LasToZlidar {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files: String = String::new();
-        let mut output_directory: String = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files: String = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_directory: String = parsed.get_string("outdir").unwrap_or_default();
         let mut compression_method = String::from("brotli");
-        let mut compression_level = 5u8;
+        let compression_level = parsed.get_i64("level").map(|v| v as u8).unwrap_or(5u8);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" || flag_val == "-input" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-outdir" {
-                output_directory = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-compress" || flag_val == "-compression" {
-                compression_method = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-            } else if flag_val == "-level" {
-                compression_level = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<u8>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<u8>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
+        if let Some(value) = parsed.get_string("compress") {
+            compression_method = value.to_string().to_lowercase();
         }
 
         if verbose {
@@ -291,7 +249,7 @@ impl WhiteboxTool for LasToZlidar {
         let output_directory = Arc::new(output_directory.clone());
         let compression_method = Arc::new(compression_method.clone());
         // let tile_list = Arc::new(Mutex::new(0..num_files));
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let inputs = inputs.clone();
@@ -300,7 +258,7 @@ impl WhiteboxTool for LasToZlidar {
             let output_directory = output_directory.clone();
             let compression_method = compression_method.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 // let mut k = 0;
                 let mut progress: usize;
                 let mut old_progress: usize = 1;
@@ -314,9 +272,7 @@ impl WhiteboxTool for LasToZlidar {
 
                     let mut input_file = inputs[k].replace("\"", "").clone();
                     if !input_file.is_empty() {
-                        if !input_file.contains(sep) && !input_file.contains("/") {
-                            input_file = format!("{}{}", working_directory, input_file);
-                        }
+                        input_file = resolve_path(&input_file, &working_directory);
 
                         match LasFile::new(&input_file, "r") {
                             Ok(input) => {
@@ -350,7 +306,9 @@ impl WhiteboxTool for LasToZlidar {
                                             / (n_points - 1) as f64)
                                             as usize;
                                         if progress != old_progress {
-                                            println!("Creating output: {}%", progress);
+                                            if tx.send(WorkerMessage::Progress("Creating output", progress)).is_err() {
+                                                return;
+                                            }
                                             old_progress = progress;
                                         }
                                     }
@@ -361,14 +319,18 @@ impl WhiteboxTool for LasToZlidar {
                                     }
                                     Err(e) => println!("Error while writing: {:?}", e),
                                 };
-                                tx.send(short_filename.clone()).unwrap();
+                                if tx.send(WorkerMessage::Data(short_filename.clone())).is_err() {
+                                    return;
+                                }
                             }
                             Err(_) => {
                                 panic!("Error reading file: {}", input_file);
                             }
                         };
                     } else {
-                        tx.send(format!("Empty file name for tile {}.", k)).unwrap();
+                        if tx.send(WorkerMessage::Data(format!("Empty file name for tile {}.", k))).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -377,7 +339,7 @@ impl WhiteboxTool for LasToZlidar {
         let mut progress: usize;
         let mut old_progress: usize = 1;
         for tile in 0..num_files {
-            let file_nm = rx.recv().expect("Error receiving data from thread.");
+            let file_nm = recv_data(&rx, reporter).expect("Error receiving data from thread.");
             if verbose && !file_nm.contains("Empty") && num_files > 1 && tile < 99 {
                 println!(
                     "Completed conversion of {} ({} of {})",
@@ -396,10 +358,11 @@ impl WhiteboxTool for LasToZlidar {
             } else if file_nm.to_lowercase().contains("empty file name") {
                 println!("{}", file_nm);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tile as f64 / (num_files - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -180,62 +180,16 @@ impl WhiteboxTool for LidarClassifySubset {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_base_file: String = "".to_string();
-        let mut input_subset_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut subset_class: u8 = 255;
-        let mut nonsubset_class: u8 = 255;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_base_file: String = parsed.get_string("base").unwrap_or_default();
+        let mut input_subset_file: String = parsed.get_string("subset").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let subset_class: u8 = parsed.get_f64("subset_class").map(|v| v as u8).unwrap_or(255);
+        let nonsubset_class: u8 = parsed.get_f64("nonsubset_class").map(|v| v as u8).unwrap_or(255);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-base" {
-                input_base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-subset" {
-                input_subset_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-subset_class" {
-                subset_class = if keyval {
-                    vec[1].to_string().parse::<u8>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<u8>().unwrap()
-                };
-            } else if flag_val == "-nonsubset_class" {
-                nonsubset_class = if keyval {
-                    vec[1].to_string().parse::<u8>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<u8>().unwrap()
-                };
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -248,16 +202,9 @@ impl WhiteboxTool for LidarClassifySubset {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_base_file.contains(sep) && !input_base_file.contains("/") {
-            input_base_file = format!("{}{}", working_directory, input_base_file);
-        }
-        if !input_subset_file.contains(sep) && !input_subset_file.contains("/") {
-            input_subset_file = format!("{}{}", working_directory, input_subset_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_base_file = resolve_input_path(&input_base_file, working_directory)?;
+        input_subset_file = resolve_input_path(&input_subset_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if subset_class > 18 {
             return Err(Error::new(
@@ -293,6 +240,7 @@ impl WhiteboxTool for LidarClassifySubset {
         let mut kdtree = KdTree::with_capacity(3, capacity_per_node);
         println!("Creating tree...");
         for i in 0..subset_lidar.header.number_of_points as usize {
+            reporter.check_cancelled()?;
             // let p: PointData = subset_lidar.get_point_info(i);
             let p = subset_lidar.get_transformed_coords(i);
             kdtree.add([p.x, p.y, p.z], i).unwrap();
@@ -584,10 +532,11 @@ impl WhiteboxTool for LidarClassifySubset {
                 output.add_point_record(pr2);
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
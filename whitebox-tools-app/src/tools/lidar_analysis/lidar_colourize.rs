@@ -9,14 +9,14 @@ License: MIT
 use whitebox_lidar::*;
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
+use whitebox_common::errors::ErrorCause;
 
 /// This tool can be used to add red-green-blue (RGB) colour values to the points contained within an
 /// input LAS file (`--in_lidar`), based on the pixel values of an overlapping input colour image (`--in_image`). 
@@ -143,48 +143,14 @@ impl WhiteboxTool for LidarColourize {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_lidar_file: String = "".to_string();
-        let mut input_image_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_lidar_file: String = parsed.get_string("in_lidar").unwrap_or_default();
+        let mut input_image_file: String = parsed.get_string("in_image").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-in_lidar" {
-                input_lidar_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-in_image" {
-                input_image_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -197,16 +163,9 @@ impl WhiteboxTool for LidarColourize {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_lidar_file.contains(sep) && !input_lidar_file.contains("/") {
-            input_lidar_file = format!("{}{}", working_directory, input_lidar_file);
-        }
-        if !input_image_file.contains(sep) && !input_image_file.contains("/") {
-            input_image_file = format!("{}{}", working_directory, input_image_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_lidar_file = resolve_input_path(&input_lidar_file, working_directory)?;
+        input_image_file = resolve_input_path(&input_image_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading input files...");
@@ -225,18 +184,14 @@ impl WhiteboxTool for LidarColourize {
 
         let mut progress: i32;
         let mut old_progress: i32 = -1;
-        let mut num_procs = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let in_lidar = in_lidar.clone();
             let in_image = in_image.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut row, mut col): (isize, isize);
                 let mut value: f64;
                 let nodata = in_image.configs.nodata;
@@ -246,10 +201,14 @@ impl WhiteboxTool for LidarColourize {
                     row = in_image.get_row_from_y(p.y);
                     col = in_image.get_column_from_x(p.x);
                     value = in_image.get_value(row, col);
-                    if value != nodata {
-                        tx.send((i, value as u32)).unwrap();
+                    if !is_nodata(value, nodata) {
+                        if tx.send((i, value as u32)).is_err() {
+                            return;
+                        }
                     } else {
-                        tx.send((i, 0u32)).unwrap();
+                        if tx.send((i, 0u32)).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -259,10 +218,11 @@ impl WhiteboxTool for LidarColourize {
         for i in 0..n_points {
             let data = rx.recv().expect("Error receiving data from thread.");
             colour_values[data.0] = data.1;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -279,8 +239,7 @@ impl WhiteboxTool for LidarColourize {
             1 | 3 | 4 | 5 => 3,      // GPS data is supplied
             6 | 7 | 8 | 9 | 10 => 3, // This is a 64-bit format and will require LAS 1.4 output support. For now, output point format 3.
             _ => {
-                return Err(Error::new(
-                    ErrorKind::InvalidInput,
+                return Err(ErrorCause::UnsupportedFormat.error(
                     "Unsupported input point record format.",
                 ))
             }
@@ -319,10 +278,11 @@ impl WhiteboxTool for LidarColourize {
                 });
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Saving data: {}%", progress);
+                    reporter.progress("Saving data", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -16,11 +16,11 @@ use whitebox_common::structures::Point2D;
 use crate::tools::*;
 use whitebox_vector::ShapefileGeometry;
 use whitebox_vector::*;
-use num_cpus;
 use std::io::{Error, ErrorKind};
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::{env, f64, fs, path, thread};
+use std::{env, f64, fs, path};
+use whitebox_common::utils::spawn;
 
 /// This tool creates a vector triangular irregular network (TIN) for a set of LiDAR points (`--input`)
 /// using a 2D [Delaunay triangulation](https://en.wikipedia.org/wiki/Delaunay_triangulation) algorithm.
@@ -175,91 +175,30 @@ impl WhiteboxTool for LidarConstructVectorTIN {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut return_type = "all".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let mut return_type = parsed.get_string("returns").unwrap_or("all".to_string());
         let mut include_class_vals = vec![true; 256];
         let mut exclude_cls_str: String;
-        let mut max_z = f64::INFINITY;
-        let mut min_z = f64::NEG_INFINITY;
+        let mut max_z = parsed.get_f64("maxz").unwrap_or(f64::INFINITY);
+        let mut min_z = parsed.get_f64("minz").unwrap_or(f64::NEG_INFINITY);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_string("exclude_cls") {
+            exclude_cls_str = value.to_string();
+            let mut cmd = exclude_cls_str.split(",");
+            let mut vec = cmd.collect::<Vec<&str>>();
+            if vec.len() == 1 {
+                cmd = exclude_cls_str.split(";");
+                vec = cmd.collect::<Vec<&str>>();
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-returns" {
-                if keyval {
-                    return_type = vec[1].to_string();
-                } else {
-                    return_type = args[i + 1].to_string();
-                }
-            } else if flag_val == "-exclude_cls" {
-                if keyval {
-                    exclude_cls_str = vec[1].to_string();
-                } else {
-                    exclude_cls_str = args[i + 1].to_string();
-                }
-                let mut cmd = exclude_cls_str.split(",");
-                let mut vec = cmd.collect::<Vec<&str>>();
-                if vec.len() == 1 {
-                    cmd = exclude_cls_str.split(";");
-                    vec = cmd.collect::<Vec<&str>>();
-                }
-                for value in vec {
-                    if !value.trim().is_empty() {
-                        let c = value.trim().parse::<usize>().unwrap();
-                        include_class_vals[c] = false;
-                    }
-                }
-            } else if flag_val == "-minz" {
-                if keyval {
-                    min_z = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    min_z = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-maxz" {
-                if keyval {
-                    max_z = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    max_z = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
+            for value in vec {
+                if !value.trim().is_empty() {
+                    let c = value.trim().parse::<usize>().unwrap();
+                    include_class_vals[c] = false;
                 }
             }
         }
@@ -369,9 +308,7 @@ impl WhiteboxTool for LidarConstructVectorTIN {
                 ));
             }
         } else {
-            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_input_path(&input_file, working_directory)?;
             inputs.push(input_file.clone());
             if output_file.is_empty() {
                 output_file = input_file
@@ -381,9 +318,7 @@ impl WhiteboxTool for LidarConstructVectorTIN {
                     .replace(".laz", ".shp")
                     .replace(".zlidar", ".shp");
             }
-            if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
             outputs.push(output_file);
         }
 
@@ -395,7 +330,7 @@ impl WhiteboxTool for LidarConstructVectorTIN {
         let tile_list = Arc::new(Mutex::new(0..num_tiles));
         let inputs = Arc::new(inputs);
         let outputs = Arc::new(outputs);
-        let num_procs2 = num_cpus::get() as isize;
+        let num_procs2 = whitebox_common::utils::ThreadPool::from_settings().num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for _ in 0..num_procs2 {
             let inputs = inputs.clone();
@@ -404,7 +339,7 @@ impl WhiteboxTool for LidarConstructVectorTIN {
             // copy over the string parameters
             let include_class_vals = include_class_vals.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut tile = 0;
                 while tile < num_tiles {
                     // Get the next tile up for interpolation
@@ -457,10 +392,11 @@ impl WhiteboxTool for LidarConstructVectorTIN {
                                 }
                             }
                         }
+                        reporter.check_cancelled()?;
                         if verbose && inputs.len() == 1 {
                             progress = (100.0_f64 * i as f64 / num_points) as usize;
                             if progress != old_progress {
-                                println!("Reading points: {}%", progress);
+                                reporter.progress("Reading points", progress);
                                 old_progress = progress;
                             }
                         }
@@ -571,11 +507,12 @@ impl WhiteboxTool for LidarConstructVectorTIN {
 
                         rec_num += 1i32;
 
+                        reporter.check_cancelled()?;
                         if verbose && num_tiles == 1 {
                             progress = (100.0_f64 * i as f64 / (result.triangles.len() - 1) as f64)
                                 as usize;
                             if progress != old_progress {
-                                println!("Creating polygons: {}%", progress);
+                                reporter.progress("Creating polygons", progress);
                                 old_progress = progress;
                             }
                         }
@@ -594,7 +531,9 @@ impl WhiteboxTool for LidarConstructVectorTIN {
                         Err(e) => panic!("Error reading file {}:\n{:?}", input_file, e),
                     };
 
-                    tx.send(tile).unwrap();
+                    if tx.send(tile).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -614,10 +553,11 @@ impl WhiteboxTool for LidarConstructVectorTIN {
                     inputs.len()
                 );
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tile as f64 / (inputs.len() - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
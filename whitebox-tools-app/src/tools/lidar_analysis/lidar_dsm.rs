@@ -11,14 +11,14 @@ use whitebox_common::algorithms::{point_in_poly, triangulate};
 use whitebox_lidar::*;
 use crate::na;
 use whitebox_raster::*;
-use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+use whitebox_common::spatial_ref_system::CoordinateReferenceSystem;
 use whitebox_common::structures::{BoundingBox, DistanceMetric, FixedRadiusSearch2D, Point2D};
 use crate::tools::*;
-use num_cpus;
 use std::io::{Error, ErrorKind};
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::{env, f64, fs, path, thread};
+use std::{env, f64, fs, path};
+use whitebox_common::utils::spawn;
 // use rayon::prelude::*;
 
 /// This tool creates a digital surface model (DSM) from a LiDAR point cloud. A DSM reflects the elevation of the tops
@@ -219,107 +219,22 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut grid_res: f64 = 1.0;
-        let mut search_radius = 0.5f64;
-        let mut max_z = f64::INFINITY;
-        let mut min_z = f64::NEG_INFINITY;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
+        let search_radius = parsed.get_f64("radius").unwrap_or(0.5f64);
+        let max_z = parsed.get_f64("maxz").unwrap_or(f64::INFINITY);
+        let min_z = parsed.get_f64("minz").unwrap_or(f64::NEG_INFINITY);
         let mut max_triangle_edge_length = f64::INFINITY;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-radius" {
-                search_radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-minz" {
-                min_z = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-maxz" {
-                max_z = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_triangle_edge_length" {
-                max_triangle_edge_length = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-
-                max_triangle_edge_length *= max_triangle_edge_length; // actually squared distance
-            }
+        if let Some(value) = parsed.get_f64("max_triangle_edge_length") {
+            max_triangle_edge_length = value;
+
+            max_triangle_edge_length *= max_triangle_edge_length; // actually squared distance
         }
 
         if verbose {
@@ -387,9 +302,7 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
                 ));
             }
         } else {
-            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_path(&input_file, working_directory);
             inputs.push(input_file.clone());
             if output_file.is_empty() {
                 output_file = input_file
@@ -398,9 +311,7 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
                     .replace(".LAS", ".tif")
                     .replace(".zlidar", ".tif");
             }
-            if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
             outputs.push(output_file);
         }
 
@@ -428,7 +339,7 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
         }
 
         let num_tiles = inputs.len();
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let inputs = Arc::new(inputs);
         let outputs = Arc::new(outputs);
         let bounding_boxes = Arc::new(bounding_boxes);
@@ -439,7 +350,7 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
             let bounding_boxes = bounding_boxes.clone();
             let tool_name = self.get_tool_name();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 for tile in (0..num_tiles).filter(|t| t % num_procs == tid) {
                     let start_run = Instant::now();
 
@@ -496,7 +407,9 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
                                             progress =
                                                 (100.0_f64 * (i + 1) as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -512,7 +425,9 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
                             "Warning: No eligible points found in {}",
                             inputs[tile].clone()
                         );
-                        tx2.send(tile).unwrap();
+                        if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                            return;
+                        }
                     } else {
                         let num_points = points.len();
                         let mut remove_pt = vec![false; num_points];
@@ -541,7 +456,9 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
                             if verbose && inputs.len() == 1 {
                                 progress = (100.0_f64 * (i + 1) as f64 / num_points as f64) as i32;
                                 if progress != old_progress {
-                                    println!("Filtering points: {}%", progress);
+                                    if tx2.send(WorkerMessage::Progress("Filtering points", progress as usize)).is_err() {
+                                        return;
+                                    }
                                     old_progress = progress;
                                 }
                             }
@@ -591,8 +508,7 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
                         configs.nodata = nodata;
                         configs.data_type = DataType::F32;
                         configs.photometric_interp = PhotometricInterpretation::Continuous;
-                        configs.epsg_code = epsg_code;
-                        configs.projection = esri_wkt_from_epsg(epsg_code);
+                        configs.set_crs(&CoordinateReferenceSystem::from_epsg(epsg_code));
 
                         let mut output = Raster::initialize_using_config(&output_file, &configs);
 
@@ -675,7 +591,9 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
                                         / (num_triangles - 1) as f64)
                                         as i32;
                                     if progress != old_progress {
-                                        println!("Progress: {}%", progress);
+                                        if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                            return;
+                                        }
                                         old_progress = progress;
                                     }
                                 }
@@ -703,7 +621,9 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
 
                         let _ = output.write().expect("Error writing file.");
 
-                        tx2.send(tile).unwrap();
+                        if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -712,7 +632,8 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
         let mut progress: i32;
         let mut old_progress: i32 = -1;
         for tile in 0..inputs.len() {
-            let tile_completed = rx2.recv().unwrap();
+            let tile_completed = recv_data(&rx2, reporter).unwrap();
+            reporter.check_cancelled()?;
             if verbose {
                 if tile <= 98 {
                     println!(
@@ -738,7 +659,7 @@ impl WhiteboxTool for LidarDigitalSurfaceModel {
                 }
                 progress = (100.0_f64 * tile as f64 / (inputs.len() - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
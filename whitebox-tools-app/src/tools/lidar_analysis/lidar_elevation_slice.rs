@@ -173,93 +173,24 @@ impl WhiteboxTool for LidarElevationSlice {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut minz = f64::NEG_INFINITY;
-        let mut maxz = f64::INFINITY;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let minz = parsed.get_f64("minz").unwrap_or(f64::NEG_INFINITY);
+        let maxz = parsed.get_f64("maxz").unwrap_or(f64::INFINITY);
         let mut filter = true;
-        let mut in_class_value = 2u8;
-        let mut out_class_value = 1u8;
+        let in_class_value = parsed.get_i64("inclassval").map(|v| v as u8).unwrap_or(2u8);
+        let out_class_value = parsed.get_i64("outclassval").map(|v| v as u8).unwrap_or(1u8);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-maxz" {
-                maxz = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-minz" {
-                minz = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-class" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    filter = false;
-                }
-            } else if flag_val == "-inclassval" {
-                in_class_value = if keyval {
-                    vec[1].to_string().parse::<u8>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<u8>().unwrap()
-                };
-            } else if flag_val == "-outclassval" {
-                out_class_value = if keyval {
-                    vec[1].to_string().parse::<u8>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<u8>().unwrap()
-                };
-            }
+        if parsed.get_bool("class") {
+            filter = false;
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("***********************************");
@@ -274,13 +205,8 @@ impl WhiteboxTool for LidarElevationSlice {
             ));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -314,10 +240,11 @@ impl WhiteboxTool for LidarElevationSlice {
                     output.add_point_record(input.get_record(i));
                     num_points_filtered += 1;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -459,10 +386,11 @@ impl WhiteboxTool for LidarElevationSlice {
                     }
                 }
                 output.add_point_record(pr2);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -9,14 +9,13 @@ License: MIT
 use whitebox_lidar::*;
 use whitebox_common::structures::{DistanceMetric, FixedRadiusSearch2D, Point3D};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to perform a slope-based classification, or filtering (i.e. removal), of
 /// non-ground points within a LiDAR point-cloud. The user must specify the name of the input and output
@@ -92,7 +91,7 @@ impl LidarGroundPointFilter {
 
         parameters.push(ToolParameter {
             name: "Minimum Number of Neighbours".to_owned(),
-            flags: vec!["--min_neighbours".to_owned()],
+            flags: vec!["--min_neighbors".to_owned(), "--min_neighbours".to_owned()],
             description: "The minimum number of neighbouring points within search areas. If fewer points than this threshold are identified during the fixed-radius search, a subsequent kNN search is performed to identify the k number of neighbours.".to_owned(),
             parameter_type: ParameterType::Integer,
             default_value: Some("0".to_owned()),
@@ -105,7 +104,7 @@ impl LidarGroundPointFilter {
             description: "Maximum inter-point slope to be considered an off-terrain point."
                 .to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("45.0".to_owned()),
+            default_value: Some("15.0".to_owned()),
             optional: true,
         });
 
@@ -124,7 +123,7 @@ impl LidarGroundPointFilter {
             flags: vec!["--classify".to_owned()],
             description: "Classify points as ground (2) or off-ground (1).".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -133,7 +132,7 @@ impl LidarGroundPointFilter {
             flags: vec!["--slope_norm".to_owned()],
             description: "Perform initial ground slope normalization?".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true,
         });
 
@@ -211,110 +210,28 @@ impl WhiteboxTool for LidarGroundPointFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut search_radius: f64 = -1.0;
-        let mut min_neighbours = 0usize;
-        let mut height_threshold: f64 = 1.0;
-        let mut slope_threshold: f64 = 15.0;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let search_radius: f64 = parsed.get_f64("radius").unwrap_or(2.0);
+        let min_neighbours = parsed.get_usize("min_neighbours")?.unwrap_or(0usize);
+        let height_threshold: f64 = parsed.get_f64("height_threshold").unwrap_or(1.0);
+        let mut slope_threshold: f64 = parsed.get_f64("slope_threshold").unwrap_or(15.0);
         let ground_class_value = 2u8;
         let otp_class_value = 1u8;
         let mut filter = true;
-        let mut slope_norm = false;
+        let slope_norm = parsed.get_bool("slope_norm");
         let mut height_above_ground = false;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if parsed.get_bool("classify") {
+            filter = false;
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-radius" {
-                search_radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-min_neighbours" || flag_val == "-min_neighbors" {
-                min_neighbours = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-height_threshold" {
-                height_threshold = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-slope_threshold" {
-                slope_threshold = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-classify" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    filter = false;
-                }
-            } else if flag_val == "-slope_norm" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    slope_norm = true;
-                }
-            } else if flag_val == "-height_above_ground" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    height_above_ground = true;
-                    filter = false; // this doesn't make sense unless non-ground points are included in the output
-                }
-            }
+        if parsed.get_bool("height_above_ground") {
+            height_above_ground = true;
+            filter = false; // this doesn't make sense unless non-ground points are included in the output
         }
 
         if verbose {
@@ -328,13 +245,8 @@ impl WhiteboxTool for LidarGroundPointFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -377,17 +289,18 @@ impl WhiteboxTool for LidarGroundPointFilter {
                     residuals[i] = p.z;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Binning points: {}%", progress);
+                    reporter.progress("Binning points", progress as usize);
                     old_progress = progress;
                 }
             }
         }
 
         let frs = Arc::new(frs); // wrap FRS in an Arc
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let input = Arc::new(input); // wrap input in an Arc
 
         if slope_norm {
@@ -400,7 +313,7 @@ impl WhiteboxTool for LidarGroundPointFilter {
                 let frs = frs.clone();
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut index_n: usize;
                     let mut z_n: f64;
                     let mut min_z: f64;
@@ -419,9 +332,13 @@ impl WhiteboxTool for LidarGroundPointFilter {
                                     min_z = z_n;
                                 }
                             }
-                            tx.send((point_num, min_z)).unwrap();
+                            if tx.send((point_num, min_z)).is_err() {
+                                return;
+                            }
                         } else {
-                            tx.send((point_num, f64::MAX)).unwrap();
+                            if tx.send((point_num, f64::MAX)).is_err() {
+                                return;
+                            }
                         }
                     }
                 });
@@ -430,10 +347,11 @@ impl WhiteboxTool for LidarGroundPointFilter {
             for i in 0..n_points {
                 let data = rx.recv().expect("Error receiving data from thread.");
                 neighbourhood_min[data.0] = data.1;
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Erosion: {}%", progress);
+                        reporter.progress("Erosion", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -448,7 +366,7 @@ impl WhiteboxTool for LidarGroundPointFilter {
                 let input = input.clone();
                 let neighbourhood_min = neighbourhood_min.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut index_n: usize;
                     let mut z_n: f64;
                     let mut max_z: f64;
@@ -467,9 +385,13 @@ impl WhiteboxTool for LidarGroundPointFilter {
                                     max_z = z_n;
                                 }
                             }
-                            tx.send((point_num, max_z)).unwrap();
+                            if tx.send((point_num, max_z)).is_err() {
+                                return;
+                            }
                         } else {
-                            tx.send((point_num, f64::MIN)).unwrap();
+                            if tx.send((point_num, f64::MIN)).is_err() {
+                                return;
+                            }
                         }
                     }
                 });
@@ -481,10 +403,11 @@ impl WhiteboxTool for LidarGroundPointFilter {
                     let z = input.get_transformed_coords(data.0).z;
                     residuals[data.0] = z - data.1;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Dilation: {}%", progress);
+                        reporter.progress("Dilation", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -501,7 +424,7 @@ impl WhiteboxTool for LidarGroundPointFilter {
             let input = input.clone();
             let residuals = residuals.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut index_n: usize;
                 let mut max_slope: f64;
                 let mut slope: f64;
@@ -530,12 +453,18 @@ impl WhiteboxTool for LidarGroundPointFilter {
                             }
                         }
                         if max_slope > slope_threshold {
-                            tx.send((point_num, true)).unwrap();
+                            if tx.send((point_num, true)).is_err() {
+                                return;
+                            }
                         } else {
-                            tx.send((point_num, false)).unwrap();
+                            if tx.send((point_num, false)).is_err() {
+                                return;
+                            }
                         }
                     } else {
-                        tx.send((point_num, true)).unwrap();
+                        if tx.send((point_num, true)).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -544,15 +473,15 @@ impl WhiteboxTool for LidarGroundPointFilter {
         for i in 0..n_points {
             let data = rx.recv().expect("Error receiving data from thread.");
             is_off_terrain[data.0] = data.1;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Slope-based Filter: {}%", progress);
+                    reporter.progress("Slope-based Filter", progress as usize);
                     old_progress = progress;
                 }
             }
         }
-        
 
         /////////////////////
         // Output the data //
@@ -569,10 +498,11 @@ impl WhiteboxTool for LidarGroundPointFilter {
                 } else {
                     num_points_filtered += 1;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -764,10 +694,11 @@ impl WhiteboxTool for LidarGroundPointFilter {
                     // Keep the classes of classified noise unaltered
                     output.add_point_record(input.get_record(point_num));
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -86,7 +86,7 @@ impl LidarHexBinning {
 
         parameters.push(ToolParameter {
             name: "Grid Orientation".to_owned(),
-            flags: vec!["--orientation".to_owned()],
+            flags: vec!["--ori".to_owned(), "--orientation".to_owned()],
             description: "Grid Orientation, 'horizontal' or 'vertical'.".to_owned(),
             parameter_type: ParameterType::OptionList(vec![
                 "horizontal".to_owned(),
@@ -164,70 +164,26 @@ impl WhiteboxTool for LidarHexBinning {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut width = 0f64;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let width = parsed.get_f64("width").unwrap_or(0f64);
         let mut orientation = String::from("h");
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-width" {
-                width = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val.contains("ori") {
-                orientation = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                if orientation.to_lowercase().contains("v") {
-                    // vertical orientation
-                    orientation = String::from("v");
-                } else {
-                    // horizontal orientation
-                    orientation = String::from("h");
-                }
+        if let Some(value) = parsed.get_string("orientation") {
+            orientation = value.to_string();
+            if orientation.to_lowercase().contains("v") {
+                // vertical orientation
+                orientation = String::from("v");
+            } else {
+                // horizontal orientation
+                orientation = String::from("h");
             }
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -251,13 +207,9 @@ impl WhiteboxTool for LidarHexBinning {
             ));
         }
 
-        if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         // Get the spatial extent
         let mut input = LasFile::new(&input_file, "r")?;
@@ -333,6 +285,7 @@ impl WhiteboxTool for LidarHexBinning {
             // first fill the FRS with the hex centre points
             let mut hex_index = 0usize;
             for row in 0..rows {
+                reporter.check_cancelled()?;
                 center_y = center_y_0 - row as f64 * three_quarter_height;
                 columns = ((extent.get_width() + half_width * (row as f64 % 2f64)) / width).ceil()
                     as usize;
@@ -373,10 +326,11 @@ impl WhiteboxTool for LidarHexBinning {
                         max_i[hex_index] = pd.intensity as i32;
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points as f64) as usize;
                     if progress != old_progress {
-                        println!("Binning points: {}%", progress);
+                        reporter.progress("Binning points", progress);
                         old_progress = progress;
                     }
                 }
@@ -420,10 +374,11 @@ impl WhiteboxTool for LidarHexBinning {
                     rec_num += 1i32;
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -483,10 +438,11 @@ impl WhiteboxTool for LidarHexBinning {
                         max_i[hex_index] = pd.intensity as i32;
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points as f64) as usize;
                     if progress != old_progress {
-                        println!("Binning points: {}%", progress);
+                        reporter.progress("Binning points", progress);
                         old_progress = progress;
                     }
                 }
@@ -529,10 +485,11 @@ impl WhiteboxTool for LidarHexBinning {
                     rec_num += 1i32;
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * col as f64 / (columns - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
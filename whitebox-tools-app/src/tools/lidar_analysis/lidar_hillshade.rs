@@ -11,14 +11,13 @@ use whitebox_lidar::*;
 use crate::na;
 use whitebox_common::structures::{DistanceMetric, FixedRadiusSearch3D};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 pub struct LidarHillshade {
     name: String,
@@ -147,80 +146,16 @@ impl WhiteboxTool for LidarHillshade {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut search_radius: f64 = -1.0;
-        let mut azimuth = 315.0f64;
-        let mut altitude = 30.0f64;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let search_radius: f64 = parsed.get_f64("radius").unwrap_or(1.0);
+        let mut azimuth = parsed.get_f64("azimuth").unwrap_or(315.0f64);
+        let mut altitude = parsed.get_f64("altitude").unwrap_or(30.0f64);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-azimuth" {
-                if keyval {
-                    azimuth = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    azimuth = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-altitude" {
-                if keyval {
-                    altitude = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    altitude = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-radius" {
-                if keyval {
-                    search_radius = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    search_radius = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -238,13 +173,8 @@ impl WhiteboxTool for LidarHillshade {
         let sin_theta = altitude.sin();
         let cos_theta = altitude.cos();
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -270,10 +200,11 @@ impl WhiteboxTool for LidarHillshade {
         for i in 0..n_points {
             let p = input.get_transformed_coords(i);
             frs.insert(p.x, p.y, p.z, i);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Binning points: {}%", progress);
+                    reporter.progress("Binning points", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -283,13 +214,13 @@ impl WhiteboxTool for LidarHillshade {
 
         let frs = Arc::new(frs); // wrap FRS in an Arc
         let input = Arc::new(input); // wrap input in an Arc
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let frs = frs.clone();
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut index_n: usize;
                 for i in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
                     let p = input.get_transformed_coords(i);
@@ -300,7 +231,9 @@ impl WhiteboxTool for LidarHillshade {
                         let p2 = input.get_transformed_coords(index_n);
                         data.push(Vector3::new(p2.x, p2.y, p2.z));
                     }
-                    tx.send((i, plane_from_points(&data))).unwrap();
+                    if tx.send((i, plane_from_points(&data))).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -308,10 +241,11 @@ impl WhiteboxTool for LidarHillshade {
         for i in 0..n_points {
             let data = rx.recv().expect("Error receiving data from thread.");
             normal_values[data.0] = data.1;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Calculating point normals: {}%", progress);
+                    reporter.progress("Calculating point normals", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -361,10 +295,11 @@ impl WhiteboxTool for LidarHillshade {
                 colour_data: rgb,
             };
             output.add_point_record(lpr);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Saving data: {}%", progress);
+                    reporter.progress("Saving data", progress as usize);
                     old_progress = progress;
                 }
             }
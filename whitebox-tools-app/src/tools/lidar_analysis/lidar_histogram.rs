@@ -16,7 +16,7 @@ use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::process::Command;
 
@@ -160,59 +160,16 @@ impl WhiteboxTool for LidarHistogram {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut parameter = "elevation".to_string();
-        let mut clip_percent = 1.0f64;
+        let mut clip_percent = parsed.get_f64("clip").unwrap_or(1.0f64);
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-parameter" {
-                parameter = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-            } else if flag_val == "-clip" {
-                clip_percent = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
+        if let Some(value) = parsed.get_string("parameter") {
+            parameter = value.to_string().to_lowercase();
         }
 
         if verbose {
@@ -226,19 +183,13 @@ impl WhiteboxTool for LidarHistogram {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: i32;
         let mut old_progress: i32 = -1;
 
         let start = Instant::now();
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -289,10 +240,11 @@ impl WhiteboxTool for LidarHistogram {
             if z > max {
                 max = z;
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Calculating min and max: {}%", progress);
+                    reporter.progress("Calculating min and max", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -317,10 +269,11 @@ impl WhiteboxTool for LidarHistogram {
                 };
                 bin = ((z - min) / bin_width).floor() as isize;
                 freq_data[bin as usize] += 1;
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Clipping the tails: {}%", progress);
+                        reporter.progress("Clipping the tails", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -383,10 +336,11 @@ impl WhiteboxTool for LidarHistogram {
                 if bin >= 0 && bin < num_bins as isize {
                     freq_data[bin as usize] += 1;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Binning the data: {}%", progress);
+                        reporter.progress("Binning the data", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -404,10 +358,11 @@ impl WhiteboxTool for LidarHistogram {
                 if bin >= 0 && bin < num_bins as isize {
                     freq_data[bin as usize] += 1;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Binning the data: {}%", progress);
+                        reporter.progress("Binning the data", progress as usize);
                         old_progress = progress;
                     }
                 }
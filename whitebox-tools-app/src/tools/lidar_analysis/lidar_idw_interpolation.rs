@@ -15,7 +15,6 @@ use whitebox_lidar::*;
 use whitebox_raster::*;
 use whitebox_common::structures::{BoundingBox, DistanceMetric, FixedRadiusSearch2D, Point3D};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs;
@@ -23,7 +22,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool interpolates LiDAR files using [inverse-distance weighting](https://en.wikipedia.org/wiki/Inverse_distance_weighting)
 /// (IDW) scheme. The user must specify the value of the IDW weight parameter (`--weight`). The output grid can be
@@ -249,167 +248,64 @@ impl WhiteboxTool for LidarIdwInterpolation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
         let mut interp_parameter = "elevation".to_string();
         let mut interp_parameter_is_rgb = false;
-        let mut return_type = "all".to_string();
-        let mut grid_res: f64 = 1.0;
-        let mut weight = 1.0;
-        let mut search_radius = 2.5;
+        let return_type = parsed.get_string("returns").unwrap_or("all".to_string());
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
+        let weight = parsed.get_f64("weight").unwrap_or(1.0);
+        let search_radius = parsed.get_f64("radius").unwrap_or(2.5);
         let mut include_class_vals = vec![true; 256];
         let mut palette = "default".to_string();
         let mut exclude_cls_str = String::new();
-        let mut max_z = f64::INFINITY;
-        let mut min_z = f64::NEG_INFINITY;
+        let max_z = parsed.get_f64("maxz").unwrap_or(f64::INFINITY);
+        let min_z = parsed.get_f64("minz").unwrap_or(f64::NEG_INFINITY);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_string("parameter") {
+            interp_parameter = value.to_string().to_lowercase();
+            if interp_parameter == "rgb" {
+                interp_parameter_is_rgb = true;
+            }
+        }
+        if let Some(value) = parsed.get_silent("palette") {
+            palette = value.to_string();
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_string("exclude_cls") {
+            exclude_cls_str = value.to_string();
+            let mut cmd = exclude_cls_str.split(",");
+            let mut vec = cmd.collect::<Vec<&str>>();
+            if vec.len() == 1 {
+                cmd = exclude_cls_str.split(";");
+                vec = cmd.collect::<Vec<&str>>();
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-parameter" {
-                interp_parameter = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-                if interp_parameter == "rgb" {
-                    interp_parameter_is_rgb = true;
-                }
-            } else if flag_val == "-returns" {
-                return_type = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-weight" {
-                weight = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-radius" {
-                search_radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-palette" {
-                palette = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-exclude_cls" {
-                exclude_cls_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                let mut cmd = exclude_cls_str.split(",");
-                let mut vec = cmd.collect::<Vec<&str>>();
-                if vec.len() == 1 {
-                    cmd = exclude_cls_str.split(";");
-                    vec = cmd.collect::<Vec<&str>>();
-                }
-                for value in vec {
-                    if !value.trim().is_empty() {
-                        if value.contains("-") {
-                            cmd = value.split("-");
-                            vec = cmd.collect::<Vec<&str>>();
-                            let c = vec[0].trim().parse::<usize>().unwrap();
-                            let d = vec[1].trim().parse::<usize>().unwrap();
-                            for e in c..=d {
-                                include_class_vals[e] = false;
-                            }
-                        } else if value.contains("...") {
-                            cmd = value.split("...");
-                            vec = cmd.collect::<Vec<&str>>();
-                            let c = vec[0].trim().parse::<usize>().unwrap();
-                            let d = vec[1].trim().parse::<usize>().unwrap();
-                            for e in c..=d {
-                                include_class_vals[e] = false;
-                            }
-                        } else {
-                            let c = value.trim().parse::<usize>().unwrap();
-                            include_class_vals[c] = false;
+            for value in vec {
+                if !value.trim().is_empty() {
+                    if value.contains("-") {
+                        cmd = value.split("-");
+                        vec = cmd.collect::<Vec<&str>>();
+                        let c = vec[0].trim().parse::<usize>().unwrap();
+                        let d = vec[1].trim().parse::<usize>().unwrap();
+                        for e in c..=d {
+                            include_class_vals[e] = false;
                         }
+                    } else if value.contains("...") {
+                        cmd = value.split("...");
+                        vec = cmd.collect::<Vec<&str>>();
+                        let c = vec[0].trim().parse::<usize>().unwrap();
+                        let d = vec[1].trim().parse::<usize>().unwrap();
+                        for e in c..=d {
+                            include_class_vals[e] = false;
+                        }
+                    } else {
+                        let c = value.trim().parse::<usize>().unwrap();
+                        include_class_vals[c] = false;
                     }
                 }
-            } else if flag_val == "-minz" {
-                min_z = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-maxz" {
-                max_z = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
             }
         }
 
@@ -494,9 +390,7 @@ impl WhiteboxTool for LidarIdwInterpolation {
                 ));
             }
         } else {
-            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_path(&input_file, working_directory);
             inputs.push(input_file.clone());
             if output_file.is_empty() {
                 output_file = input_file
@@ -507,9 +401,7 @@ impl WhiteboxTool for LidarIdwInterpolation {
                     .replace(".LAZ", ".tif")
                     .replace(".zlidar", ".tif");
             }
-            if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
             outputs.push(output_file);
         }
 
@@ -538,12 +430,8 @@ impl WhiteboxTool for LidarIdwInterpolation {
         let inputs = Arc::new(inputs);
         let outputs = Arc::new(outputs);
         let bounding_boxes = Arc::new(bounding_boxes);
-        let mut num_procs2 = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs2 {
-            num_procs2 = max_procs;
-        }
+        let num_procs2 = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
         let (tx2, rx2) = mpsc::channel();
         for _ in 0..num_procs2 {
             let inputs = inputs.clone();
@@ -558,7 +446,7 @@ impl WhiteboxTool for LidarIdwInterpolation {
             let exclude_cls_str = exclude_cls_str.clone();
             let include_class_vals = include_class_vals.clone();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut tile = 0;
                 while tile < num_tiles {
                     // Get the next tile up for interpolation
@@ -631,7 +519,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Binning points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Binning points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -659,7 +549,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Binning points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Binning points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -687,7 +579,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Binning points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Binning points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -719,7 +613,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Binning points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Binning points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -751,7 +647,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -783,7 +681,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -828,7 +728,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -857,7 +759,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Binning points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Binning points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -869,7 +773,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
 
                     if frs.size() == 0 {
                         println!("Warning: No points found in {}.", inputs[tile].clone());
-                        tx2.send(tile).unwrap();
+                        if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                            return;
+                        }
                     } else {
                         let west: f64 = bounding_boxes[tile].min_x;
                         let north: f64 = bounding_boxes[tile].max_y;
@@ -958,7 +864,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                 if verbose && inputs.len() == 1 {
                                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                                     if progress != old_progress {
-                                        println!("Progress: {}%", progress);
+                                        if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                            return;
+                                        }
                                         old_progress = progress;
                                     }
                                 }
@@ -966,17 +874,13 @@ impl WhiteboxTool for LidarIdwInterpolation {
                         } else {
                             // there's only one tile, so use all cores to interpolate this one tile.
                             let frs = Arc::new(frs); // wrap FRS in an Arc
-                            let mut num_procs = num_cpus::get() as isize;
                             let configs = whitebox_common::configs::get_configs().unwrap();
-                            let max_procs = configs.max_procs;
-                            if max_procs > 0 && max_procs < num_procs {
-                                num_procs = max_procs;
-                            }
+                            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
                             let (tx, rx) = mpsc::channel();
                             for tid in 0..num_procs {
                                 let frs = frs.clone();
                                 let tx1 = tx.clone();
-                                thread::spawn(move || {
+                                spawn(move || {
                                     let (mut x, mut y): (f64, f64);
                                     let mut zn: f64;
                                     let mut dist: f64;
@@ -1035,7 +939,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                                 }
                                             }
                                         }
-                                        tx1.send((row, data)).unwrap();
+                                        if tx1.send((row, data)).is_err() {
+                                            return;
+                                        }
                                     }
                                 });
                             }
@@ -1046,7 +952,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
                                 if verbose {
                                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                                     if progress != old_progress {
-                                        println!("Progress: {}%", progress);
+                                        if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                            return;
+                                        }
                                         old_progress = progress;
                                     }
                                 }
@@ -1080,7 +988,9 @@ impl WhiteboxTool for LidarIdwInterpolation {
 
                         let _ = output.write().unwrap();
 
-                        tx2.send(tile).unwrap();
+                        if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -1089,7 +999,8 @@ impl WhiteboxTool for LidarIdwInterpolation {
         let mut progress: i32;
         let mut old_progress: i32 = -1;
         for tile in 0..inputs.len() {
-            let tile_completed = rx2.recv().unwrap();
+            let tile_completed = recv_data(&rx2, reporter).unwrap();
+            reporter.check_cancelled()?;
             if verbose {
                 if tile <= 98 {
                     println!(
@@ -1115,7 +1026,7 @@ impl WhiteboxTool for LidarIdwInterpolation {
                 }
                 progress = (100.0_f64 * tile as f64 / (inputs.len() - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 01/06/2017
-Last Modified: 30/01/2022
+Last Modified: 16/10/2026
 License: MIT
 */
 
@@ -16,6 +16,7 @@ use std::io::prelude::*;
 use std::io::BufWriter;
 use std::io::{Error, ErrorKind};
 use std::path;
+use serde_json::json;
 use std::process::Command;
 use std::u16;
 use whitebox_common::structures::{ Point2D, Point3D };
@@ -24,6 +25,17 @@ use whitebox_common::algorithms::{ convex_hull, polygon_area };
 /// This tool can be used to print basic information about the data contained within a LAS file, used to store LiDAR
 /// data. The reported information will include including data on the header, point return frequency, and classification
 /// data and information about the variable length records (VLRs) and geokeys.
+///
+/// The summary is written as an HTML report by default. If the output file (`--output`) has a `.json` extension, the
+/// summary is instead written as structured JSON, including the header fields, the VLR list, the return-number and
+/// class histograms, the intensity and GPS time ranges, the point density, and the coordinate reference system, which
+/// is convenient for QC scripting.
+///
+/// When the `--validate` flag is specified, the file is checked against the ASPRS LAS specification and any anomalies
+/// are reported, including points with zero return numbers or numbers of returns, return numbers that exceed the
+/// number of returns, point coordinates outside of the header bounding box, header point counts by return that do not
+/// match the point records, point formats unsupported by the file version, invalid scale factors, and points assigned
+/// to reserved classes.
 pub struct LidarInfo {
     name: String,
     description: String,
@@ -52,10 +64,10 @@ impl LidarInfo {
         parameters.push(ToolParameter {
             name: "Output Summary Report File".to_owned(),
             flags: vec!["-o".to_owned(), "--output".to_owned()],
-            description: "Output HTML file for summary report.".to_owned(),
+            description: "Output HTML or JSON file for summary report.".to_owned(),
             parameter_type: ParameterType::NewFile(ParameterFileType::Html),
             default_value: None,
-            optional: false,
+            optional: true,
         });
 
         parameters.push(ToolParameter {
@@ -65,7 +77,7 @@ impl LidarInfo {
                 "Flag indicating whether or not to calculate the average point density and nominal point spacing."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -76,7 +88,7 @@ impl LidarInfo {
                 "Flag indicating whether or not to print the variable length records (VLRs)."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -85,7 +97,16 @@ impl LidarInfo {
             flags: vec!["--geokeys".to_owned()],
             description: "Flag indicating whether or not to print the geokeys.".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Validate the file against the LAS specification?".to_owned(),
+            flags: vec!["--validate".to_owned()],
+            description: "Flag indicating whether or not to check the file for anomalies.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -103,8 +124,8 @@ impl LidarInfo {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=file.las --vlr --geokeys\"
-.*{0} -r={1} --wd=\"*path*to*data*\" -i=file.las",
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=file.las -o=outfile.html --vlr --geokeys
+.*{0} -r={1} --wd=\"*path*to*data*\" -i=file.las -o=summary.json --validate",
             short_exe, name
         )
         .replace("*", &sep);
@@ -159,55 +180,15 @@ impl WhiteboxTool for LidarInfo {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file = String::new();
-        let mut show_vlrs = false;
-        let mut show_geokeys = false;
-        let mut keyval: bool;
-        let mut show_density = false;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-density" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    show_density = true;
-                }
-            } else if flag_val == "-vlr" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    show_vlrs = true;
-                }
-            } else if flag_val == "-geokeys" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    show_geokeys = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let show_vlrs = parsed.get_bool("vlr");
+        let show_geokeys = parsed.get_bool("geokeys");
+        let show_density = parsed.get_bool("density");
+        let validate = parsed.get_bool("validate");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -220,14 +201,11 @@ impl WhiteboxTool for LidarInfo {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = std::path::MAIN_SEPARATOR;
         // if !working_directory.ends_with(sep) {
         //     working_directory.push_str(&(sep.to_string()));
         // }
 
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
         if output_file.is_empty() {
             let extension: String = match path::Path::new(&input_file).extension().expect("Error: the input file name must have an extension").to_str() {
@@ -237,6 +215,244 @@ impl WhiteboxTool for LidarInfo {
             output_file = input_file.replace(&extension, "_summary.html");
         }
 
+        let mut input = LasFile::new(&input_file, "r")?;
+
+        let num_points = input.header.number_of_points;
+        let mut min_i = u16::MAX;
+        let mut max_i = u16::MIN;
+        let mut intensity: u16;
+        let mut num_first: i64 = 0;
+        let mut num_last: i64 = 0;
+        let mut num_only: i64 = 0;
+        let mut num_intermediate: i64 = 0;
+        let mut ret: u8;
+        let mut nrets: u8;
+        let mut pd: PointData;
+        let mut ret_array: [i32; 5] = [0; 5];
+        let mut class_array: [i32; 256] = [0; 256];
+        // counts by return number, for the validation against the header's by-return counts
+        let mut ret_counts: [u64; 16] = [0; 16];
+        let mut min_gps_time = f64::INFINITY;
+        let mut max_gps_time = f64::NEG_INFINITY;
+        let mut num_zero_return_number = 0u64;
+        let mut num_zero_number_of_returns = 0u64;
+        let mut num_return_exceeds_returns = 0u64;
+        let mut num_out_of_bounds = 0u64;
+        let tol_x = input.header.x_scale_factor.abs() / 2f64;
+        let tol_y = input.header.y_scale_factor.abs() / 2f64;
+        let tol_z = input.header.z_scale_factor.abs() / 2f64;
+        let (ret_mask, nrets_mask, nrets_shift) = if input.header.point_format > 5 {
+            (0b0000_1111u8, 0b1111_0000u8, 4u8)
+        } else {
+            (0b0000_0111u8, 0b0011_1000u8, 3u8)
+        };
+        // read the points into a Vec<Point2D>
+        let mut points: Vec<Point2D> = Vec::with_capacity(input.header.number_of_points as usize);
+        let mut p: Point3D;
+        for i in 0..input.header.number_of_points as usize {
+            reporter.check_cancelled()?;
+            pd = input[i]; 
+            p = input.get_transformed_coords(i);
+            points.push(Point2D::new(p.x, p.y));
+            if p.x < input.header.min_x - tol_x
+                || p.x > input.header.max_x + tol_x
+                || p.y < input.header.min_y - tol_y
+                || p.y > input.header.max_y + tol_y
+                || p.z < input.header.min_z - tol_z
+                || p.z > input.header.max_z + tol_z
+            {
+                num_out_of_bounds += 1;
+            }
+            // The return_number and number_of_returns methods substitute 1 for a zero value,
+            // which the specification does not allow, so the raw bit fields are checked here.
+            let raw_ret = pd.point_bit_field & ret_mask;
+            let raw_nrets = (pd.point_bit_field & nrets_mask) >> nrets_shift;
+            if raw_ret == 0 {
+                num_zero_return_number += 1;
+            }
+            if raw_nrets == 0 {
+                num_zero_number_of_returns += 1;
+            }
+            if raw_ret > 0 && raw_nrets > 0 && raw_ret > raw_nrets {
+                num_return_exceeds_returns += 1;
+            }
+            ret_counts[raw_ret as usize] += 1;
+            if let Some(t) = input.get_gps_time(i) {
+                if t < min_gps_time {
+                    min_gps_time = t;
+                }
+                if t > max_gps_time {
+                    max_gps_time = t;
+                }
+            }
+            ret = pd.return_number();
+            if ret > 5 {
+                // Return is too high
+                ret = 5;
+            }
+            ret_array[(ret - 1) as usize] += 1;
+            nrets = pd.number_of_returns();
+            class_array[pd.classification() as usize] += 1;
+            if nrets == 1 {
+                num_only += 1;
+            } else if ret == 1 && nrets > 1 {
+                num_first += 1;
+            } else if ret == nrets {
+                num_last += 1;
+            } else {
+                num_intermediate += 1;
+            }
+            intensity = pd.intensity;
+            if intensity > max_i {
+                max_i = intensity;
+            }
+            if intensity < min_i {
+                min_i = intensity;
+            }
+        }
+
+        let mut density = f64::NAN;
+        let mut spacing = f64::NAN;
+        if show_density {
+            let hull_points = convex_hull(&mut points);
+            let area = polygon_area(&hull_points);
+            density = (input.header.number_of_points as f64) / area;
+            spacing = 1f64 / density.sqrt();
+        }
+
+        let anomalies = if validate {
+            validate_las_file(
+                &input,
+                &ret_counts,
+                &class_array,
+                num_zero_return_number,
+                num_zero_number_of_returns,
+                num_return_exceeds_returns,
+                num_out_of_bounds,
+            )
+        } else {
+            vec![]
+        };
+
+        if output_file.to_lowercase().ends_with(".json") {
+            // JSON has no representation of NaN or infinite values
+            let num = |v: f64| {
+                if v.is_finite() {
+                    json!(v)
+                } else {
+                    serde_json::Value::Null
+                }
+            };
+            let header = &input.header;
+            let mut returns = serde_json::Map::new();
+            for r in 1..16 {
+                if ret_counts[r] > 0 {
+                    returns.insert(r.to_string(), json!(ret_counts[r]));
+                }
+            }
+            let mut classes = vec![];
+            for c in 0..256 {
+                if class_array[c] > 0 {
+                    classes.push(json!({
+                        "class": c,
+                        "name": convert_class_val_to_class_string(c as u8),
+                        "count": class_array[c],
+                        "percent": class_array[c] as f64 / num_points as f64 * 100f64,
+                    }));
+                }
+            }
+            let mut summary = json!({
+                "file": input_file,
+                "header": {
+                    "file_signature": header.file_signature,
+                    "file_source_id": header.file_source_id,
+                    "global_encoding": header.global_encoding.value,
+                    "version": format!("{}.{}", header.version_major, header.version_minor),
+                    "system_id": header.system_id.trim_matches(char::from(0)).trim(),
+                    "generating_software": header.generating_software.trim_matches(char::from(0)).trim(),
+                    "file_creation_day": header.file_creation_day,
+                    "file_creation_year": header.file_creation_year,
+                    "header_size": header.header_size,
+                    "offset_to_points": header.offset_to_points,
+                    "number_of_vlrs": header.number_of_vlrs,
+                    "number_of_extended_vlrs": header.number_of_extended_vlrs,
+                    "point_format": header.point_format,
+                    "point_record_length": header.point_record_length,
+                    "number_of_points": header.number_of_points,
+                    "number_of_points_by_return": header.number_of_points_by_return.to_vec(),
+                    "scale_factor": [header.x_scale_factor, header.y_scale_factor, header.z_scale_factor],
+                    "offset": [header.x_offset, header.y_offset, header.z_offset],
+                    "min": [header.min_x, header.min_y, header.min_z],
+                    "max": [header.max_x, header.max_y, header.max_z],
+                },
+                "intensity": {
+                    "min": if num_points > 0 { json!(min_i) } else { serde_json::Value::Null },
+                    "max": if num_points > 0 { json!(max_i) } else { serde_json::Value::Null },
+                },
+                "gps_time": {
+                    "min": num(min_gps_time),
+                    "max": num(max_gps_time),
+                },
+                "returns": {
+                    "by_return_number": returns,
+                    "only": num_only,
+                    "first": num_first,
+                    "intermediate": num_intermediate,
+                    "last": num_last,
+                },
+                "classes": classes,
+                "crs": {
+                    "epsg": input.get_epsg_code(),
+                    "wkt": input.get_wkt(),
+                },
+            });
+            if show_density {
+                summary["density"] = json!({
+                    "points_per_square_unit": num(density),
+                    "nominal_point_spacing": num(spacing),
+                });
+            }
+            if show_vlrs {
+                let vlrs: Vec<serde_json::Value> = input
+                    .vlr_data
+                    .iter()
+                    .map(|vlr| {
+                        json!({
+                            "user_id": vlr.user_id.trim_matches(char::from(0)).trim(),
+                            "record_id": vlr.record_id,
+                            "record_length_after_header": vlr.record_length_after_header,
+                            "description": vlr.description.trim_matches(char::from(0)).trim(),
+                        })
+                    })
+                    .collect();
+                summary["vlrs"] = json!(vlrs);
+            }
+            if show_geokeys {
+                summary["geokeys"] = json!(input.geokeys.interpret_geokeys());
+            }
+            if validate {
+                let list: Vec<serde_json::Value> = anomalies
+                    .iter()
+                    .map(|a| json!({ "code": a.0, "description": a.1, "count": a.2 }))
+                    .collect();
+                summary["validation"] = json!({
+                    "valid": anomalies.is_empty(),
+                    "anomalies": list,
+                });
+            }
+
+            let json_str = match serde_json::to_string_pretty(&summary) {
+                Ok(s) => s,
+                Err(e) => return Err(Error::new(ErrorKind::Other, format!("{}", e))),
+            };
+            let mut f = File::create(&output_file)?;
+            f.write_all(json_str.as_bytes())?;
+            if verbose {
+                println!("Complete! Please see {} for output.", output_file);
+            }
+            return Ok(());
+        }
+
         let f = File::create(output_file.clone())?;
         let mut writer = BufWriter::new(f);
 
@@ -305,64 +521,23 @@ impl WhiteboxTool for LidarInfo {
         ";
         writer.write_all(s.as_bytes())?;
 
-        let input = LasFile::new(&input_file, "r")?;
-
         let s1 = &format!("<h2>File Summary</h2><p>{}", input);
         writer.write_all(s1.replace("\n", "<br>").as_bytes())?;
 
-        let num_points = input.header.number_of_points;
-        let mut min_i = u16::MAX;
-        let mut max_i = u16::MIN;
-        let mut intensity: u16;
-        let mut num_first: i64 = 0;
-        let mut num_last: i64 = 0;
-        let mut num_only: i64 = 0;
-        let mut num_intermediate: i64 = 0;
-        let mut ret: u8;
-        let mut nrets: u8;
-        let mut pd: PointData;
-        // let mut p: Point3D;
-        let mut ret_array: [i32; 5] = [0; 5];
-        let mut class_array: [i32; 256] = [0; 256];
-        // read the points into a Vec<Point2D>
-        let mut points: Vec<Point2D> = Vec::with_capacity(input.header.number_of_points as usize);
-        let mut p: Point3D;
-        for i in 0..input.header.number_of_points as usize {
-            pd = input[i]; 
-            p = input.get_transformed_coords(i);
-            points.push(Point2D::new(p.x, p.y));
-            ret = pd.return_number();
-            if ret > 5 {
-                // Return is too high
-                ret = 5;
-            }
-            ret_array[(ret - 1) as usize] += 1;
-            nrets = pd.number_of_returns();
-            class_array[pd.classification() as usize] += 1;
-            if nrets == 1 {
-                num_only += 1;
-            } else if ret == 1 && nrets > 1 {
-                num_first += 1;
-            } else if ret == nrets {
-                num_last += 1;
-            } else {
-                num_intermediate += 1;
-            }
-            intensity = pd.intensity;
-            if intensity > max_i {
-                max_i = intensity;
-            }
-            if intensity < min_i {
-                min_i = intensity;
-            }
-        }
-
         // println!("\n\nMin I: {}\nMax I: {}", min_i, max_i);
         let s1 = &format!(
-            "<br>Min Intensity: {}<br>Max Intensity: {}</p>",
+            "<br>Min Intensity: {}<br>Max Intensity: {}",
             min_i, max_i
         );
         writer.write_all(s1.as_bytes())?;
+        if min_gps_time <= max_gps_time {
+            let s1 = &format!(
+                "<br>Min GPS Time: {}<br>Max GPS Time: {}",
+                min_gps_time, max_gps_time
+            );
+            writer.write_all(s1.as_bytes())?;
+        }
+        writer.write_all("</p>".as_bytes())?;
 
         s = "<h2>Point Returns Analysis</h2>";
         writer.write_all(s.as_bytes())?;
@@ -487,11 +662,6 @@ impl WhiteboxTool for LidarInfo {
         writer.write_all(s.as_bytes())?;
 
         if show_density {
-            let hull_points = convex_hull(&mut points);
-            let area = polygon_area(&hull_points);
-            let density = (input.header.number_of_points as f64) / area;
-            let spacing = 1f64 / density.sqrt();
-
             let s1 = &format!("<p>Average point density: {:.3} pts / m<sup>2</sup><br>Nominal point spacing: {:.4} m</p>", density, spacing);
             writer.write_all(s1.as_bytes()).expect("Error writing to file.");
         }
@@ -520,6 +690,37 @@ impl WhiteboxTool for LidarInfo {
             writer.write_all(s1.as_bytes())?;
         }
 
+        if validate {
+            s = "<h2>Validation</h2>";
+            writer.write_all(s.as_bytes())?;
+            if anomalies.is_empty() {
+                s = "<p>No anomalies were found.</p>";
+                writer.write_all(s.as_bytes())?;
+            } else {
+                s = "<p><table>
+                <caption>Anomalies</caption>
+                <tr>
+                    <th class=\"headerCell\">Code</th>
+                    <th class=\"headerCell\">Description</th>
+                    <th class=\"headerCell\">Count</th>
+                </tr>";
+                writer.write_all(s.as_bytes())?;
+                for a in &anomalies {
+                    let s1 = &format!(
+                        "<tr>
+                        <td>{}</td>
+                        <td>{}</td>
+                        <td class=\"numberCell\">{}</td>
+                    </tr>\n",
+                        a.0, a.1, a.2
+                    );
+                    writer.write_all(s1.as_bytes())?;
+                }
+                s = "</table></p>";
+                writer.write_all(s.as_bytes())?;
+            }
+        }
+
         s = "</body>";
         writer.write_all(s.as_bytes())?;
 
@@ -557,3 +758,122 @@ impl WhiteboxTool for LidarInfo {
         Ok(())
     }
 }
+
+/// Checks a LAS file against the ASPRS LAS specification, returning a list of anomalies as
+/// (code, description, count) tuples.
+fn validate_las_file(
+    input: &LasFile,
+    ret_counts: &[u64; 16],
+    class_array: &[i32; 256],
+    num_zero_return_number: u64,
+    num_zero_number_of_returns: u64,
+    num_return_exceeds_returns: u64,
+    num_out_of_bounds: u64,
+) -> Vec<(&'static str, String, u64)> {
+    let header = &input.header;
+    let mut anomalies = vec![];
+    if header.file_signature != "LASF" {
+        anomalies.push((
+            "FILE_SIGNATURE",
+            format!("The file signature ('{}') is not 'LASF'.", header.file_signature),
+            1,
+        ));
+    }
+    let min_version_minor = match header.point_format {
+        0 | 1 => Some(0),
+        2 | 3 => Some(2),
+        4 | 5 => Some(3),
+        6..=10 => Some(4),
+        _ => None,
+    };
+    match min_version_minor {
+        Some(minor) => {
+            if header.version_major != 1 || header.version_minor < minor {
+                anomalies.push((
+                    "POINT_FORMAT_VERSION",
+                    format!(
+                        "Point format {} is not supported by LAS version {}.{}.",
+                        header.point_format, header.version_major, header.version_minor
+                    ),
+                    1,
+                ));
+            }
+        }
+        None => anomalies.push((
+            "POINT_FORMAT",
+            format!("Point format {} is not defined by the specification.", header.point_format),
+            1,
+        )),
+    }
+    if header.x_scale_factor <= 0f64 || header.y_scale_factor <= 0f64 || header.z_scale_factor <= 0f64 {
+        anomalies.push((
+            "SCALE_FACTOR",
+            "One or more of the header scale factors is not positive.".to_string(),
+            1,
+        ));
+    }
+    if header.number_of_points > 0
+        && (header.min_x > header.max_x || header.min_y > header.max_y || header.min_z > header.max_z)
+    {
+        anomalies.push((
+            "HEADER_BOUNDS",
+            "The header minimum coordinates exceed the maximum coordinates.".to_string(),
+            1,
+        ));
+    }
+    if num_zero_return_number > 0 {
+        anomalies.push((
+            "ZERO_RETURN_NUMBER",
+            "Points have a return number of zero.".to_string(),
+            num_zero_return_number,
+        ));
+    }
+    if num_zero_number_of_returns > 0 {
+        anomalies.push((
+            "ZERO_NUMBER_OF_RETURNS",
+            "Points have a number of returns of zero.".to_string(),
+            num_zero_number_of_returns,
+        ));
+    }
+    if num_return_exceeds_returns > 0 {
+        anomalies.push((
+            "RETURN_EXCEEDS_NUMBER_OF_RETURNS",
+            "Points have a return number greater than their number of returns.".to_string(),
+            num_return_exceeds_returns,
+        ));
+    }
+    if num_out_of_bounds > 0 {
+        anomalies.push((
+            "OUT_OF_BOUNDS",
+            "Points lie outside of the header bounding box.".to_string(),
+            num_out_of_bounds,
+        ));
+    }
+    let mut count_difference = 0u64;
+    for r in 1..16 {
+        let header_count = header.number_of_points_by_return[r - 1];
+        count_difference += if header_count > ret_counts[r] {
+            header_count - ret_counts[r]
+        } else {
+            ret_counts[r] - header_count
+        };
+    }
+    if count_difference > 0 {
+        anomalies.push((
+            "POINTS_BY_RETURN",
+            "The header point counts by return do not match the point records; the count is the total difference."
+                .to_string(),
+            count_difference,
+        ));
+    }
+    // Classes 23-63 are reserved for ASPRS definition; formats 0-5 can only store classes 0-31.
+    let reserved: i64 = class_array[23..64].iter().map(|&c| c as i64).sum();
+    if reserved > 0 {
+        anomalies.push((
+            "RESERVED_CLASS",
+            "Points are assigned to classes reserved by the ASPRS (23-63).".to_string(),
+            reserved as u64,
+        ));
+    }
+    anomalies
+}
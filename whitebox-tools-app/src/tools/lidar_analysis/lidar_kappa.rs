@@ -2,19 +2,19 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 24/09/2017
-Last Modified: 04/01/2019
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_lidar::*;
 use whitebox_raster::*;
 use whitebox_common::structures::{Array2D, DistanceMetric, FixedRadiusSearch3D, Point3D};
+use whitebox_common::rendering::Report;
+use crate::tools::math_stat_analysis::add_kappa_analysis;
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::path::Path;
 use std::process::Command;
@@ -78,7 +78,7 @@ impl LidarKappaIndex {
 
         parameters.push(ToolParameter {
             name: "Output Class Accuracy Raster File".to_owned(),
-            flags: vec!["--class_accuracy".to_owned()],
+            flags: vec!["--class_ac".to_owned(), "--class_accuracy".to_owned()],
             description: "Output classification accuracy raster file.".to_owned(),
             parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
             default_value: None,
@@ -159,67 +159,14 @@ impl WhiteboxTool for LidarKappaIndex {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1 = String::new();
-        let mut input_file2 = String::new();
-        let mut output_file = String::new();
-        let mut class_accuracy_file = String::new();
-        let mut grid_res: f64 = 1.0;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i1" || flag_val == "-input1" {
-                input_file1 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-i2" || flag_val == "-input2" {
-                input_file2 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val.contains("-class_ac") {
-                class_accuracy_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut class_accuracy_file = parsed.get_string("class_accuracy").unwrap_or_default();
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -232,26 +179,16 @@ impl WhiteboxTool for LidarKappaIndex {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: i32;
         let mut old_progress: i32 = -1;
 
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
         if !output_file.ends_with(".html") {
             output_file = output_file + ".html";
         }
-        if !class_accuracy_file.contains(&sep) && !class_accuracy_file.contains("/") {
-            class_accuracy_file = format!("{}{}", working_directory, class_accuracy_file);
-        }
+        class_accuracy_file = resolve_path(&class_accuracy_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -317,10 +254,11 @@ impl WhiteboxTool for LidarKappaIndex {
             // p1 = input2.get_point_info(i);
             p1 = input2.get_transformed_coords(i);
             frs.insert(p1.x, p1.y, p1.z, i);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points2 as f64) as i32;
                 if progress != old_progress {
-                    println!("Binning points: {}%", progress);
+                    reporter.progress("Binning points", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -354,10 +292,11 @@ impl WhiteboxTool for LidarKappaIndex {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points1 as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -375,307 +314,38 @@ impl WhiteboxTool for LidarKappaIndex {
                     class_accuracy.set_value(row, col, nodata);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
         }
 
-        let mut num_classes = 0;
-        for a in 0..256usize {
-            if active_class[a] {
-                num_classes += 1;
-            }
-        }
-
-        let mut agreements = 0usize;
-        let mut expected_frequency = 0f64;
-        let mut n = 0usize;
-        let mut row_total: usize;
-        let mut col_total: usize;
-        let kappa: f64;
-        let overall_accuracy: f64;
-
-        for a in 0..256usize {
-            agreements += error_matrix[a][a];
-            for b in 0..256usize {
-                n += error_matrix[a][b];
-            }
-        }
-
-        for a in 0..256usize {
-            row_total = 0;
-            col_total = 0;
-            for b in 0..256usize {
-                col_total += error_matrix[a][b];
-                row_total += error_matrix[b][a];
-            }
-            expected_frequency += (col_total as f64 * row_total as f64) / (n as f64);
-        }
-
-        kappa = (agreements as f64 - expected_frequency as f64)
-            / (n as f64 - expected_frequency as f64);
-        overall_accuracy = agreements as f64 / n as f64;
-
-        let mut f = File::create(output_file.as_str()).unwrap();
-
-        let mut s = "<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
-        <head>
-            <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
-            <title>Lidar Kappa Index of Agreement</title>
-            <style  type=\"text/css\">
-                h1 {
-                    font-size: 14pt;
-                    margin-left: 15px;
-                    margin-right: 15px;
-                    text-align: center;
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                }
-                h3 {
-                    font-size: 12pt;
-                    margin-left: 15px;
-                    margin-right: 15px;
-                    text-align: left;
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                }
-                p, ol, ul, li {
-                    font-size: 12pt;
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                    margin-left: 15px;
-                    margin-right: 15px;
-                }
-                caption {
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                    font-size: 12pt;
-                    margin-left: 15px;
-                    margin-right: 15px;
-                }
-                table {
-                    font-size: 12pt;
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                    font-family: arial, sans-serif;
-                    border-collapse: collapse;
-                    align: center;
-                }
-                td {
-                    text-align: left;
-                    padding: 8px;
-                }
-                th {
-                    text-align: left;
-                    padding: 8px;
-                    background-color: #ffffff;
-                    border-bottom: 1px solid #333333;
-                    text-align: center;
-                }
-                tr:nth-child(1) {
-                    border-bottom: 1px solid #333333;
-                    border-top: 2px solid #333333;
-                }
-                tr:last-child {
-                    border-bottom: 2px solid #333333;
-                }
-                tr:nth-child(even) {
-                    background-color: #dddddd;
-                }
-                .numberCell {
-                    text-align: right;
-                }
-                .headerCell {
-                    text-align: center;
-                }
-            </style>
-        </head>
-        <body>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<body><h1>Kappa Index of Agreement</h1>";
-        f.write(s.as_bytes()).unwrap();
-        let path = Path::new(&input_file1);
-        let s1 = &format!(
-            "<p><strong>Classification Data:</strong> {}</p>",
-            path.file_name().unwrap().to_str().unwrap()
-        );
-        f.write_all(s1.as_bytes())?;
-        let path = Path::new(&input_file2);
-        let s1 = &format!(
-            "<p><strong>Reference Data:</strong> {}</p><br>",
-            path.file_name().unwrap().to_str().unwrap()
-        );
-        f.write_all(s1.as_bytes())?;
-        // let s2 = &format!("{}{}{}{}{}", "<p><b>Input Data:</b> <br><br><b>Classification Data:</b> ", input_file1, "<br><br><b>Reference Data:</b> ", input_file2, "<p>");
-        // f.write(s2.as_bytes()).unwrap();
-
-        s = "<br><table>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<caption>Contingency Table</caption>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<tr>";
-        f.write(s.as_bytes()).unwrap();
-        let s3 = &format!(
-            "{}{}{}",
-            "<th colspan=\"2\" rowspan=\"2\"></th><th colspan=\"",
-            num_classes,
-            "\">Reference Data</th><th rowspan=\"2\">Row<br>Totals</th>"
-        );
-        f.write(s3.as_bytes()).unwrap();
-        s = "</tr>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<tr>";
-        f.write(s.as_bytes()).unwrap();
-        for a in 0..256 {
-            if active_class[a] {
-                let s = &format!(
-                    "{}{}{}",
-                    "<th>",
-                    convert_class_val_to_class_string(a as u8),
-                    "</th>"
-                );
-                f.write(s.as_bytes()).unwrap();
-            }
-        }
-
-        s = "</tr>";
-        f.write(s.as_bytes()).unwrap();
-        let mut first_entry = true;
-        for a in 0..256 {
-            if active_class[a] {
-                if first_entry {
-                    let s = format!(
-                        "{}{}{}{}{}",
-                        "<tr><td rowspan=\"",
-                        num_classes,
-                        "\" valign=\"center\"><b>Class<br>Data</b></td> <td><b>",
-                        convert_class_val_to_class_string(a as u8),
-                        "</b></td>"
-                    );
-                    f.write(s.as_bytes()).unwrap();
-                } else {
-                    let s = format!(
-                        "{}{}{}",
-                        "<tr><td><b>",
-                        convert_class_val_to_class_string(a as u8),
-                        "</b></td>"
-                    );
-                    f.write(s.as_bytes()).unwrap();
-                }
-                row_total = 0;
-                for b in 0..256 {
-                    if active_class[b] {
-                        row_total += error_matrix[a][b];
-                        let s = format!(
-                            "{}{}{}",
-                            "<td class=\"numberCell\">", error_matrix[a][b], "</td>"
-                        );
-                        f.write(s.as_bytes()).unwrap();
-                    }
-                }
-                let s = format!("{}{}{}", "<td class=\"numberCell\">", row_total, "</td>");
-                f.write(s.as_bytes()).unwrap();
-
-                let s2 = "</tr>";
-                f.write(s2.as_bytes()).unwrap();
-                first_entry = false;
-            }
-        }
-        s = "<tr>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<th colspan=\"2\">Column Totals</th>";
-        f.write(s.as_bytes()).unwrap();
-        for a in 0..256 {
-            if active_class[a] {
-                col_total = 0;
-                for b in 0..256 {
-                    if active_class[b] {
-                        col_total += error_matrix[b][a];
-                    }
-                }
-                let s = &format!("{}{}{}", "<td  class=\"numberCell\">", col_total, "</td>");
-                f.write(s.as_bytes()).unwrap();
-            }
-        }
-
-        let s4 = &format!(
-            "{}{}{}",
-            "<td class=\"numberCell\"><b>N</b>=", n, "</td></tr>"
-        );
-        f.write(s4.as_bytes()).unwrap();
-        s = "</table>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<br><br><table>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<caption>Class Statistics</caption>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<tr><th class=\"headerCell\">Class</th><th class=\"headerCell\">User's Accuracy<sup>1</sup><br>(Reliability)</th><th class=\"headerCell\">Producer's Accuracy<sup>1</sup><br>(Accuracy)</th></tr>";
-        f.write(s.as_bytes()).unwrap();
-
-        let mut average_producers = 0.0;
-        let mut average_users = 0.0;
-        let mut num_active = 0.0;
-        for a in 0..256 {
-            if active_class[a] {
-                num_active += 1.0;
-                let mut row_total = 0;
-                let mut col_total = 0;
-                for b in 0..256 {
-                    if active_class[b] {
-                        col_total += error_matrix[a][b];
-                        row_total += error_matrix[b][a];
-                    }
-                }
-                average_users += 100.0 * error_matrix[a][a] as f64 / col_total as f64;
-                average_producers += 100.0 * error_matrix[a][a] as f64 / row_total as f64;
-                let s = &format!(
-                    "{}{}{}{}{}{}{}",
-                    "<tr><td>",
-                    convert_class_val_to_class_string(a as u8),
-                    "</td><td class=\"numberCell\">",
-                    format!(
-                        "{:.*}",
-                        2,
-                        (100.0 * error_matrix[a][a] as f64 / col_total as f64)
-                    ),
-                    "%</td><td class=\"numberCell\">",
-                    format!(
-                        "{:.*}",
-                        2,
-                        (100.0 * error_matrix[a][a] as f64 / row_total as f64)
-                    ),
-                    "%</td></tr>"
-                );
-                f.write(s.as_bytes()).unwrap();
-            }
-        }
-        f.write(format!("<tr><td>Average</td><td class=\"numberCell\">{}%</td><td class=\"numberCell\">{}%</td></tr>", format!("{:.*}", 2, average_users / num_active),
-                format!("{:.*}", 2, average_producers / num_active)).as_bytes()).unwrap();
-
-        s = "</table>";
-        f.write(s.as_bytes()).unwrap();
-        let s6 = &format!(
-            "<p>{}{}</p>",
-            "<p><b>Overall Accuracy</b> = ",
-            format!("{:.*}%", 2, overall_accuracy * 100.0)
-        );
-        f.write(s6.as_bytes()).unwrap();
-        let s7 = &format!(
-            "<p><b>Kappa</b><sup>2</sup> = {}</p>",
-            format!("{:.*}", 3, kappa)
-        );
-        f.write(s7.as_bytes()).unwrap();
-        let s5 = &format!("{}{}", "<p><br>Notes:<br>1. User's accuracy refers to the proportion of points correctly assigned to a class (i.e. the number of points correctly classified for a category divided by the row total in the contingency table) and is a measure of the reliability. ",
-                "Producer's accuracy is a measure of the proportion of the points in each category correctly classified (i.e. the number of points correctly classified for a category divided by the column total in the contingency table) and is a measure of the accuracy.<br>");
-        f.write(s5.as_bytes()).unwrap();
-        f.write("<br>2. Cohen's kappa coefficient is a statistic that measures inter-rater agreement for qualitative (categorical)
-        items. It is generally thought to be a more robust measure than simple percent agreement calculation, since
-        kappa takes into account the agreement occurring by chance. Kappa measures the percentage of data values in the
-        main diagonal of the contingency table and then adjusts these values for the amount of agreement that could be expected due
-        to chance alone.</p>".as_bytes()).unwrap();
-        s = "</body>";
-        f.write_all(s.as_bytes())?;
-
-        let _ = f.flush();
+        // the error matrix and labels of the classes that occur in either file
+        let classes: Vec<usize> = (0..256usize).filter(|&a| active_class[a]).collect();
+        let labels: Vec<String> = classes
+            .iter()
+            .map(|&a| convert_class_val_to_class_string(a as u8))
+            .collect();
+        let matrix: Vec<Vec<usize>> = classes
+            .iter()
+            .map(|&a| classes.iter().map(|&b| error_matrix[a][b]).collect())
+            .collect();
+
+        let file_name = |f: &str| {
+            Path::new(f)
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        let mut report = Report::new("Lidar Kappa Index of Agreement");
+        report.add_property("Classification Data", file_name(&input_file1));
+        report.add_property("Reference Data", file_name(&input_file2));
+        add_kappa_analysis(&mut report, &labels, &matrix);
+        report.write(&output_file)?;
 
         if verbose {
             if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
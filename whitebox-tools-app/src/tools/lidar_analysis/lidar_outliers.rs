@@ -9,15 +9,14 @@ License: MIT
 use whitebox_lidar::*;
 use whitebox_common::structures::{DistanceMetric, FixedRadiusSearch2D, Point3D};
 use crate::tools::*;
-use num_cpus;
 use std::cmp::Ordering::Equal;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool will filter out points from a LiDAR point cloud if the absolute elevation
 /// difference between a point and the averge elevation of its neighbourhood, calculated
@@ -90,7 +89,7 @@ impl LidarRemoveOutliers {
             flags: vec!["--classify".to_owned()],
             description: "Classify points as ground (2) or off-ground (1).".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -159,76 +158,19 @@ impl WhiteboxTool for LidarRemoveOutliers {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut search_radius = 2f64;
-        let mut elev_diff = 50f64;
-        let mut use_median = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let search_radius = parsed.get_f64("radius").unwrap_or(2f64);
+        let elev_diff = parsed.get_f64("elev_diff").unwrap_or(50f64);
+        let use_median = parsed.get_bool("use_median");
         let mut filter = true;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-radius" {
-                search_radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-elev_diff" {
-                elev_diff = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-use_median" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    use_median = true;
-                }
-            } else if flag_val == "-classify" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    filter = false;
-                }
-            }
+        if parsed.get_bool("classify") {
+            filter = false;
         }
 
         if verbose {
@@ -242,13 +184,8 @@ impl WhiteboxTool for LidarRemoveOutliers {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -279,10 +216,11 @@ impl WhiteboxTool for LidarRemoveOutliers {
             if !pd.is_classified_noise() && !pd.withheld() {
                 frs.insert(p.x, p.y, p.z);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Adding points to search tree: {}%", progress);
+                    reporter.progress("Adding points to search tree", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -290,14 +228,14 @@ impl WhiteboxTool for LidarRemoveOutliers {
 
         let frs = Arc::new(frs); // wrap FRS in an Arc
         let input = Arc::new(input); // wrap input in an Arc
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
         if !use_median {
             for tid in 0..num_procs {
                 let frs = frs.clone();
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut avg_z: f64;
                     let mut n: f64;
                     let mut p: Point3D;
@@ -314,9 +252,13 @@ impl WhiteboxTool for LidarRemoveOutliers {
                             }
                         }
                         if n > 0f64 {
-                            tx.send((point_num, p.z - avg_z / n)).unwrap();
+                            if tx.send((point_num, p.z - avg_z / n)).is_err() {
+                                return;
+                            }
                         } else {
-                            tx.send((point_num, p.z)).unwrap();
+                            if tx.send((point_num, p.z)).is_err() {
+                                return;
+                            }
                         }
                     }
                 });
@@ -326,7 +268,7 @@ impl WhiteboxTool for LidarRemoveOutliers {
                 let frs = frs.clone();
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut n: usize;
                     let mut median: f64;
                     let mut p: Point3D;
@@ -351,13 +293,19 @@ impl WhiteboxTool for LidarRemoveOutliers {
                                 // even num neighbours
                                 median = (z_values[n / 2 - 1] + z_values[n / 2]) / 2f64;
                             }
-                            tx.send((point_num, p.z - median)).unwrap();
+                            if tx.send((point_num, p.z - median)).is_err() {
+                                return;
+                            }
                         } else if n == 2 {
                             median = (z_values[0] + z_values[1]) / 2f64;
-                            tx.send((point_num, p.z - median)).unwrap();
+                            if tx.send((point_num, p.z - median)).is_err() {
+                                return;
+                            }
                         } else {
                             // n == 0 or n == 1 {
-                            tx.send((point_num, p.z)).unwrap();
+                            if tx.send((point_num, p.z)).is_err() {
+                                return;
+                            }
                         }
                     }
                 });
@@ -368,10 +316,11 @@ impl WhiteboxTool for LidarRemoveOutliers {
         for i in 0..n_points {
             let data = rx.recv().expect("Error receiving data from thread.");
             residuals[data.0] = data.1;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -389,10 +338,11 @@ impl WhiteboxTool for LidarRemoveOutliers {
                 } else {
                     num_points_filtered += 1;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -536,10 +486,11 @@ impl WhiteboxTool for LidarRemoveOutliers {
                     }
                 }
                 output.add_point_record(pr2);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress as usize);
                         old_progress = progress;
                     }
                 }
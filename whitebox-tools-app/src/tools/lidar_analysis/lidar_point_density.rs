@@ -15,7 +15,6 @@ use whitebox_lidar::*;
 use whitebox_raster::*;
 use whitebox_common::structures::{BoundingBox, DistanceMetric, FixedRadiusSearch2D, Point3D};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs;
@@ -23,7 +22,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 pub struct LidarPointDensity {
     name: String,
@@ -195,124 +194,33 @@ impl WhiteboxTool for LidarPointDensity {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut return_type = "all".to_string();
-        let mut grid_res: f64 = 1.0;
-        let mut search_radius = 2.5f64;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let return_type = parsed.get_string("returns").unwrap_or("all".to_string());
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
+        let search_radius = parsed.get_f64("radius").unwrap_or(2.5f64);
         let mut include_class_vals = vec![true; 256];
-        let mut palette = "default".to_string();
+        let palette = parsed.get_string("palette").unwrap_or("default".to_string());
         let mut exclude_cls_str = String::new();
-        let mut max_z = f64::INFINITY;
-        let mut min_z = f64::NEG_INFINITY;
+        let max_z = parsed.get_f64("maxz").unwrap_or(f64::INFINITY);
+        let min_z = parsed.get_f64("minz").unwrap_or(f64::NEG_INFINITY);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_string("exclude_cls") {
+            exclude_cls_str = value.to_string();
+            let mut cmd = exclude_cls_str.split(",");
+            let mut vec = cmd.collect::<Vec<&str>>();
+            if vec.len() == 1 {
+                cmd = exclude_cls_str.split(";");
+                vec = cmd.collect::<Vec<&str>>();
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-returns" {
-                if keyval {
-                    return_type = vec[1].to_string();
-                } else {
-                    return_type = args[i + 1].to_string();
-                }
-            } else if flag_val == "-resolution" {
-                if keyval {
-                    grid_res = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    grid_res = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-radius" {
-                if keyval {
-                    search_radius = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    search_radius = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-palette" {
-                if keyval {
-                    palette = vec[1].to_string();
-                } else {
-                    palette = args[i + 1].to_string();
-                }
-            } else if flag_val == "-exclude_cls" {
-                if keyval {
-                    exclude_cls_str = vec[1].to_string();
-                } else {
-                    exclude_cls_str = args[i + 1].to_string();
-                }
-                let mut cmd = exclude_cls_str.split(",");
-                let mut vec = cmd.collect::<Vec<&str>>();
-                if vec.len() == 1 {
-                    cmd = exclude_cls_str.split(";");
-                    vec = cmd.collect::<Vec<&str>>();
-                }
-                for value in vec {
-                    if !value.trim().is_empty() {
-                        let c = value.trim().parse::<usize>().unwrap();
-                        include_class_vals[c] = false;
-                    }
-                }
-            } else if flag_val == "-minz" {
-                if keyval {
-                    min_z = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    min_z = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-maxz" {
-                if keyval {
-                    max_z = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    max_z = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
+            for value in vec {
+                if !value.trim().is_empty() {
+                    let c = value.trim().parse::<usize>().unwrap();
+                    include_class_vals[c] = false;
                 }
             }
         }
@@ -421,9 +329,7 @@ impl WhiteboxTool for LidarPointDensity {
                 ));
             }
         } else {
-            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_path(&input_file, working_directory);
             inputs.push(input_file.clone());
             if output_file.is_empty() {
                 output_file = input_file
@@ -432,9 +338,7 @@ impl WhiteboxTool for LidarPointDensity {
                     .replace(".LAS", ".tif")
                     .replace(".zlidar", ".tif");
             }
-            if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
             outputs.push(output_file);
         }
 
@@ -463,7 +367,7 @@ impl WhiteboxTool for LidarPointDensity {
         let inputs = Arc::new(inputs);
         let outputs = Arc::new(outputs);
         let bounding_boxes = Arc::new(bounding_boxes);
-        let num_procs2 = num_cpus::get() as isize;
+        let num_procs2 = whitebox_common::utils::ThreadPool::from_settings().num_threads() as isize;
         let (tx2, rx2) = mpsc::channel();
         for _ in 0..num_procs2 {
             let inputs = inputs.clone();
@@ -477,7 +381,7 @@ impl WhiteboxTool for LidarPointDensity {
             let exclude_cls_str = exclude_cls_str.clone();
             let include_class_vals = include_class_vals.clone();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut tile = 0;
                 while tile < num_tiles {
                     // Get the next tile up for interpolation
@@ -547,7 +451,9 @@ impl WhiteboxTool for LidarPointDensity {
                                 if verbose && inputs.len() == 1 {
                                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                                     if progress != old_progress {
-                                        println!("Binning points: {}%", progress);
+                                        if tx2.send(WorkerMessage::Progress("Binning points", progress as usize)).is_err() {
+                                            return;
+                                        }
                                         old_progress = progress;
                                     }
                                 }
@@ -598,17 +504,13 @@ impl WhiteboxTool for LidarPointDensity {
                     } else {
                         // there's only one tile, so use all cores to interpolate this one tile.
                         let frs = Arc::new(frs); // wrap FRS in an Arc
-                        let mut num_procs = num_cpus::get() as isize;
                         let configs = whitebox_common::configs::get_configs().unwrap();
-                        let max_procs = configs.max_procs;
-                        if max_procs > 0 && max_procs < num_procs {
-                            num_procs = max_procs;
-                        }
+                        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
                         let (tx, rx) = mpsc::channel();
                         for tid in 0..num_procs {
                             let frs = frs.clone();
                             let tx1 = tx.clone();
-                            thread::spawn(move || {
+                            spawn(move || {
                                 let (mut x, mut y): (f64, f64);
                                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                                     let mut data = vec![nodata; columns as usize];
@@ -618,7 +520,9 @@ impl WhiteboxTool for LidarPointDensity {
                                         let ret = frs.search(x, y);
                                         data[col as usize] = ret.len() as f64 / search_area;
                                     }
-                                    tx1.send((row, data)).unwrap();
+                                    if tx1.send((row, data)).is_err() {
+                                        return;
+                                    }
                                 }
                             });
                         }
@@ -629,7 +533,9 @@ impl WhiteboxTool for LidarPointDensity {
                             if verbose {
                                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                                 if progress != old_progress {
-                                    println!("Progress: {}%", progress);
+                                    if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                        return;
+                                    }
                                     old_progress = progress;
                                 }
                             }
@@ -658,7 +564,9 @@ impl WhiteboxTool for LidarPointDensity {
 
                     let _ = output.write().unwrap();
 
-                    tx2.send(tile).unwrap();
+                    if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -666,7 +574,7 @@ impl WhiteboxTool for LidarPointDensity {
         let mut progress: i32;
         let mut old_progress: i32 = -1;
         for tile in 0..inputs.len() {
-            let tile_completed = rx2.recv().unwrap();
+            let tile_completed = recv_data(&rx2, reporter).unwrap();
             if verbose {
                 println!(
                     "Finished interpolating {} ({} of {})",
@@ -678,10 +586,11 @@ impl WhiteboxTool for LidarPointDensity {
                     inputs.len()
                 );
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tile as f64 / (inputs.len() - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
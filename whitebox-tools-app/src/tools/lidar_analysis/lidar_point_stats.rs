@@ -10,7 +10,6 @@ use whitebox_lidar::*;
 use whitebox_raster::*;
 use whitebox_common::structures::{Array2D, Point3D};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs;
@@ -18,7 +17,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool creates several rasters summarizing the distribution of LiDAR points in a LAS data file.
 /// The user must specify the name of an input LAS file (`--input`) and the output raster grid
@@ -94,7 +93,7 @@ impl LidarPointStats {
                 "Flag indicating whether or not to output the number of points (returns) raster."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("True".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true,
         });
 
@@ -114,13 +113,13 @@ impl LidarPointStats {
             description: "Flag indicating whether or not to output the average number of points (returns) per pulse raster."
                 .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("True".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true,
         });
 
         parameters.push(ToolParameter {
             name: "Output elevation range?".to_owned(),
-            flags: vec!["--z_range".to_owned()],
+            flags: vec!["--elev_range".to_owned(), "--z_range".to_owned()],
             description: "Flag indicating whether or not to output the elevation range raster."
                 .to_owned(),
             parameter_type: ParameterType::Boolean,
@@ -130,7 +129,7 @@ impl LidarPointStats {
 
         parameters.push(ToolParameter {
             name: "Output intensity range?".to_owned(),
-            flags: vec!["--intensity_range".to_owned()],
+            flags: vec!["--i_range".to_owned(), "--intensity_range".to_owned()],
             description: "Flag indicating whether or not to output the intensity range raster."
                 .to_owned(),
             parameter_type: ParameterType::Boolean,
@@ -140,7 +139,7 @@ impl LidarPointStats {
 
         parameters.push(ToolParameter {
             name: "Output predominant class?".to_owned(),
-            flags: vec!["--predom_class".to_owned()],
+            flags: vec!["--predominant_class".to_owned(), "--predom_class".to_owned()],
             description:
                 "Flag indicating whether or not to output the predominant classification raster."
                     .to_owned(),
@@ -218,77 +217,19 @@ impl WhiteboxTool for LidarPointStats {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut grid_res: f64 = 1.0;
-        let mut num_points = false;
-        let mut num_pulses = false;
-        let mut avg_points_per_pulse = false;
-        let mut z_range = false;
-        let mut intensity_range = false;
-        let mut predominant_class = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_file: String = parsed.get_string("input").unwrap_or_default();
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
+        let mut num_points = parsed.get_bool("num_points");
+        let mut num_pulses = parsed.get_bool("num_pulses");
+        let mut avg_points_per_pulse = parsed.get_bool("avg_points_per_pulse");
+        let mut z_range = parsed.get_bool("z_range");
+        let mut intensity_range = parsed.get_bool("intensity_range");
+        let mut predominant_class = parsed.get_bool("predom_class");
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_points" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    num_points = true;
-                }
-            } else if flag_val == "-num_pulses" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    num_pulses = true;
-                }
-            } else if flag_val == "-z_range" || flag_val == "elev_range" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    z_range = true;
-                }
-            } else if flag_val == "-intensity_range" || flag_val == "i_range" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    intensity_range = true;
-                }
-            } else if flag_val == "-predom_class" || flag_val == "-predominant_class" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    predominant_class = true;
-                }
-            } else if flag_val == "-avg_points_per_pulse" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    avg_points_per_pulse = true;
-                }
-            }
-        }
 
         let start = Instant::now();
 
@@ -353,7 +294,7 @@ impl WhiteboxTool for LidarPointStats {
         let num_tiles = inputs.len();
         let tile_list = Arc::new(Mutex::new(0..num_tiles));
         let inputs = Arc::new(inputs);
-        let num_procs2 = num_cpus::get() as isize;
+        let num_procs2 = whitebox_common::utils::ThreadPool::from_settings().num_threads() as isize;
         let (tx2, rx2) = mpsc::channel();
         for _ in 0..num_procs2 {
             let inputs = inputs.clone();
@@ -361,7 +302,7 @@ impl WhiteboxTool for LidarPointStats {
             // copy over the string parameters
             let tool_name = self.get_tool_name();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut tile = 0;
                 while tile < num_tiles {
                     // Get the next tile up for interpolation
@@ -454,7 +395,9 @@ impl WhiteboxTool for LidarPointStats {
                             if verbose && inputs.len() == 1 {
                                 progress = (100.0_f64 * i as f64 / num_points_float) as i32;
                                 if progress != old_progress {
-                                    println!("Progress: {}%", progress);
+                                    if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                        return;
+                                    }
                                     old_progress = progress;
                                 }
                             }
@@ -630,7 +573,9 @@ impl WhiteboxTool for LidarPointStats {
                             if verbose && inputs.len() == 1 {
                                 progress = (100.0_f64 * i as f64 / num_points_float) as i32;
                                 if progress != old_progress {
-                                    println!("Progress: {}%", progress);
+                                    if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                        return;
+                                    }
                                     old_progress = progress;
                                 }
                             }
@@ -721,7 +666,9 @@ impl WhiteboxTool for LidarPointStats {
                             if verbose && inputs.len() == 1 {
                                 progress = (100.0_f64 * i as f64 / num_points_float) as i32;
                                 if progress != old_progress {
-                                    println!("Progress: {}%", progress);
+                                    if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                        return;
+                                    }
                                     old_progress = progress;
                                 }
                             }
@@ -749,7 +696,9 @@ impl WhiteboxTool for LidarPointStats {
                         drop(out_predominant_class);
                     }
 
-                    tx2.send(tile).unwrap();
+                    if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -757,7 +706,7 @@ impl WhiteboxTool for LidarPointStats {
         let mut progress: i32;
         let mut old_progress: i32 = -1;
         for tile in 0..inputs.len() {
-            let tile_completed = rx2.recv().unwrap();
+            let tile_completed = recv_data(&rx2, reporter).unwrap();
             if verbose {
                 println!(
                     "Finished {} ({} of {})",
@@ -771,10 +720,11 @@ impl WhiteboxTool for LidarPointStats {
                     inputs.len()
                 );
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tile as f64 / (inputs.len() - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
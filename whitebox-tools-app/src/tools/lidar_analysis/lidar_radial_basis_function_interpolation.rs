@@ -18,7 +18,6 @@ use crate::tools::*;
 use kdtree::distance::squared_euclidean;
 use kdtree::KdTree;
 use nalgebra::DVector;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs;
@@ -26,7 +25,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 pub struct LidarRbfInterpolation {
     name: String,
@@ -112,7 +111,7 @@ impl LidarRbfInterpolation {
             flags: vec!["--num_points".to_owned()],
             description: "Number of points.".to_owned(),
             parameter_type: ParameterType::Integer,
-            default_value: Some("20".to_string()),
+            default_value: Some("15".to_string()),
             optional: false,
         });
 
@@ -179,7 +178,7 @@ impl LidarRbfInterpolation {
             flags: vec!["--weight".to_owned()],
             description: "Weight parameter used in basis function.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("5".to_owned()),
+            default_value: Some("0.1".to_owned()),
             optional: false,
         });
 
@@ -248,206 +247,82 @@ impl WhiteboxTool for LidarRbfInterpolation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
         let mut interp_parameter = "elevation".to_string();
         // let mut interp_parameter_is_rgb = false;
-        let mut return_type = "all".to_string();
-        let mut grid_res: f64 = 1.0;
+        let return_type = parsed.get_string("returns").unwrap_or("all".to_string());
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
         let search_radius = 1.0;
         let mut include_class_vals = vec![true; 256];
         let mut palette = "default".to_string();
         let mut exclude_cls_str = String::new();
-        let mut max_z = f64::INFINITY;
-        let mut min_z = f64::NEG_INFINITY;
+        let max_z = parsed.get_f64("maxz").unwrap_or(f64::INFINITY);
+        let min_z = parsed.get_f64("minz").unwrap_or(f64::NEG_INFINITY);
         let mut func_type = String::from("ThinPlateSpline");
         let mut poly_order = 0usize;
-        let mut weight = 0.1f64;
-        let mut num_points = 15usize;
+        let mut weight = parsed.get_f64("weight").unwrap_or(0.1f64);
+        let num_points = parsed.get_usize("num_points")?.unwrap_or(15usize);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_string("parameter") {
+            interp_parameter = value.to_string().to_lowercase();
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_silent("palette") {
+            palette = value.to_string();
+        }
+        if let Some(value) = parsed.get_string("exclude_cls") {
+            exclude_cls_str = value.to_string();
+            let mut cmd = exclude_cls_str.split(",");
+            let mut vec = cmd.collect::<Vec<&str>>();
+            if vec.len() == 1 {
+                cmd = exclude_cls_str.split(";");
+                vec = cmd.collect::<Vec<&str>>();
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-parameter" {
-                interp_parameter = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-            } else if flag_val == "-returns" {
-                return_type = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-weight" {
-                weight = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            // } else if flag_val == "-radius" {
-            //     search_radius = if keyval {
-            //         vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-            //     } else {
-            //         args[i + 1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-            //     };
-            } else if flag_val == "-palette" {
-                palette = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-exclude_cls" {
-                exclude_cls_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                let mut cmd = exclude_cls_str.split(",");
-                let mut vec = cmd.collect::<Vec<&str>>();
-                if vec.len() == 1 {
-                    cmd = exclude_cls_str.split(";");
-                    vec = cmd.collect::<Vec<&str>>();
-                }
-                for value in vec {
-                    if !value.trim().is_empty() {
-                        if value.contains("-") {
-                            cmd = value.split("-");
-                            vec = cmd.collect::<Vec<&str>>();
-                            let c = vec[0].trim().parse::<usize>().unwrap();
-                            let d = vec[1].trim().parse::<usize>().unwrap();
-                            for e in c..=d {
-                                include_class_vals[e] = false;
-                            }
-                        } else if value.contains("...") {
-                            cmd = value.split("...");
-                            vec = cmd.collect::<Vec<&str>>();
-                            let c = vec[0].trim().parse::<usize>().unwrap();
-                            let d = vec[1].trim().parse::<usize>().unwrap();
-                            for e in c..=d {
-                                include_class_vals[e] = false;
-                            }
-                        } else {
-                            let c = value.trim().parse::<usize>().unwrap();
-                            include_class_vals[c] = false;
+            for value in vec {
+                if !value.trim().is_empty() {
+                    if value.contains("-") {
+                        cmd = value.split("-");
+                        vec = cmd.collect::<Vec<&str>>();
+                        let c = vec[0].trim().parse::<usize>().unwrap();
+                        let d = vec[1].trim().parse::<usize>().unwrap();
+                        for e in c..=d {
+                            include_class_vals[e] = false;
+                        }
+                    } else if value.contains("...") {
+                        cmd = value.split("...");
+                        vec = cmd.collect::<Vec<&str>>();
+                        let c = vec[0].trim().parse::<usize>().unwrap();
+                        let d = vec[1].trim().parse::<usize>().unwrap();
+                        for e in c..=d {
+                            include_class_vals[e] = false;
                         }
+                    } else {
+                        let c = value.trim().parse::<usize>().unwrap();
+                        include_class_vals[c] = false;
                     }
                 }
-            } else if flag_val == "-minz" {
-                min_z = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-maxz" {
-                max_z = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-func_type" {
-                func_type = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-            } else if flag_val == "-poly_order" {
-                let s = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-                poly_order = if s.contains("none") {
-                    0usize
-                } else if s.contains("const") {
-                    1usize
-                } else {
-                    2usize
-                };
-            } else if flag_val == "-weight" {
-                weight = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_points" {
-                num_points = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
             }
         }
+        if let Some(value) = parsed.get_string("func_type") {
+            func_type = value.to_string().to_lowercase();
+        }
+        if let Some(value) = parsed.get_string("poly_order") {
+            let s = value.to_string().to_lowercase();
+            poly_order = if s.contains("none") {
+                0usize
+            } else if s.contains("const") {
+                1usize
+            } else {
+                2usize
+            };
+        }
+        if let Some(value) = parsed.get_f64("weight") {
+            weight = value;
+        }
 
         let basis_func = if func_type.contains("thin") {
             Basis::ThinPlateSpine(weight)
@@ -543,9 +418,7 @@ impl WhiteboxTool for LidarRbfInterpolation {
                 ));
             }
         } else {
-            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_path(&input_file, working_directory);
             inputs.push(input_file.clone());
             if output_file.is_empty() {
                 output_file = input_file
@@ -556,9 +429,7 @@ impl WhiteboxTool for LidarRbfInterpolation {
                     .replace(".LAZ", ".tif")
                     .replace(".zlidar", ".tif");
             }
-            if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
             outputs.push(output_file);
         }
 
@@ -588,7 +459,7 @@ impl WhiteboxTool for LidarRbfInterpolation {
         let inputs = Arc::new(inputs);
         let outputs = Arc::new(outputs);
         let bounding_boxes = Arc::new(bounding_boxes);
-        let num_procs2 = num_cpus::get() as isize;
+        let num_procs2 = whitebox_common::utils::ThreadPool::from_settings().num_threads() as isize;
         let (tx2, rx2) = mpsc::channel();
         for _ in 0..num_procs2 {
             let func_type = func_type.clone();
@@ -604,7 +475,7 @@ impl WhiteboxTool for LidarRbfInterpolation {
             let exclude_cls_str = exclude_cls_str.clone();
             let include_class_vals = include_class_vals.clone();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut tile = 0;
                 while tile < num_tiles {
                     // Get the next tile up for interpolation
@@ -696,7 +567,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -738,7 +611,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -780,7 +655,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -822,7 +699,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -864,7 +743,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -906,7 +787,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -962,7 +845,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -1005,7 +890,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                         if verbose && inputs.len() == 1 {
                                             progress = (100.0_f64 * i as f64 / num_points) as i32;
                                             if progress != old_progress {
-                                                println!("Reading points: {}%", progress);
+                                                if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                    return;
+                                                }
                                                 old_progress = progress;
                                             }
                                         }
@@ -1017,7 +904,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
 
                     if points.len() == 0 {
                         println!("Warning: No points found in {}", inputs[tile].clone());
-                        tx2.send(tile).unwrap();
+                        if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                            return;
+                        }
                     } else {
                         let range = max_value - min_value;
                         let range_threshold = range * 1f64; // only estimated values that are +/- 0.5 range beyond the min and max values will be output
@@ -1090,7 +979,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                 if verbose && inputs.len() == 1 {
                                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                                     if progress != old_progress {
-                                        println!("Progress: {}%", progress);
+                                        if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                            return;
+                                        }
                                         old_progress = progress;
                                     }
                                 }
@@ -1100,19 +991,15 @@ impl WhiteboxTool for LidarRbfInterpolation {
                             let points = Arc::new(points);
                             let z_values = Arc::new(z_values);
                             let tree = Arc::new(tree);
-                            let mut num_procs = num_cpus::get() as isize;
                             let configs = whitebox_common::configs::get_configs().unwrap();
-                            let max_procs = configs.max_procs;
-                            if max_procs > 0 && max_procs < num_procs {
-                                num_procs = max_procs;
-                            }
+                            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
                             let (tx, rx) = mpsc::channel();
                             for tid in 0..num_procs {
                                 let tree = tree.clone();
                                 let tx1 = tx.clone();
                                 let points = points.clone();
                                 let z_values = z_values.clone();
-                                thread::spawn(move || {
+                                spawn(move || {
                                     let (mut x, mut y): (f64, f64);
                                     let mut zn: f64;
                                     let mut point_num: usize;
@@ -1144,7 +1031,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                                 }
                                             }
                                         }
-                                        tx1.send((row, data)).unwrap();
+                                        if tx1.send((row, data)).is_err() {
+                                            return;
+                                        }
                                     }
                                 });
                             }
@@ -1155,7 +1044,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
                                 if verbose {
                                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                                     if progress != old_progress {
-                                        println!("Progress: {}%", progress);
+                                        if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                            return;
+                                        }
                                         old_progress = progress;
                                     }
                                 }
@@ -1195,7 +1086,9 @@ impl WhiteboxTool for LidarRbfInterpolation {
 
                         let _ = output.write().unwrap();
 
-                        tx2.send(tile).unwrap();
+                        if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -1204,7 +1097,8 @@ impl WhiteboxTool for LidarRbfInterpolation {
         let mut progress: i32;
         let mut old_progress: i32 = -1;
         for tile in 0..inputs.len() {
-            let tile_completed = rx2.recv().unwrap();
+            let tile_completed = recv_data(&rx2, reporter).unwrap();
+            reporter.check_cancelled()?;
             if verbose {
                 if tile <= 98 {
                     println!(
@@ -1230,7 +1124,7 @@ impl WhiteboxTool for LidarRbfInterpolation {
                 }
                 progress = (100.0_f64 * tile as f64 / (inputs.len() - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
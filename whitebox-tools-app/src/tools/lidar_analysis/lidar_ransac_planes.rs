@@ -15,14 +15,13 @@ use crate::tools::*;
 use rand::seq::SliceRandom;
 // use kdtree::distance::squared_euclidean;
 // use kdtree::KdTree;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool uses the [random sample consensus (RANSAC)](https://en.wikipedia.org/wiki/Random_sample_consensus)
 /// method to identify points within a LiDAR point cloud that belong to planar surfaces. RANSAC is a common
@@ -111,7 +110,7 @@ impl LidarRansacPlanes {
             flags: vec!["--num_samples".to_owned()],
             description: "Number of sample points on which to build the model.".to_owned(),
             parameter_type: ParameterType::Integer,
-            default_value: Some("5".to_owned()),
+            default_value: Some("10".to_owned()),
             optional: true,
         });
 
@@ -120,7 +119,7 @@ impl LidarRansacPlanes {
             flags: vec!["--threshold".to_owned()],
             description: "Threshold used to determine inlier points.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("0.35".to_owned()),
+            default_value: Some("0.15".to_owned()),
             optional: true,
         });
 
@@ -129,7 +128,7 @@ impl LidarRansacPlanes {
             flags: vec!["--model_size".to_owned()],
             description: "Acceptable model size.".to_owned(),
             parameter_type: ParameterType::Integer,
-            default_value: Some("8".to_owned()),
+            default_value: Some("30".to_owned()),
             optional: true,
         });
 
@@ -138,7 +137,7 @@ impl LidarRansacPlanes {
             flags: vec!["--max_slope".to_owned()],
             description: "Maximum planar slope.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("80.0".to_owned()),
+            default_value: Some("75.0".to_owned()),
             optional: true,
         });
 
@@ -225,128 +224,23 @@ impl WhiteboxTool for LidarRansacPlanes {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut search_radius = 2f64;
-        let mut num_iter = 50;
-        let mut num_samples = 10;
-        let mut threshold = 0.15;
-        let mut acceptable_model_size = 30;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let search_radius = parsed.get_f64("radius").unwrap_or(2f64);
+        let num_iter = parsed.get_usize("num_iter")?.unwrap_or(50);
+        let mut num_samples = parsed.get_usize("num_samples")?.unwrap_or(10);
+        let threshold = parsed.get_f64("threshold").unwrap_or(0.15);
+        let mut acceptable_model_size = parsed.get_usize("model_size")?.unwrap_or(30);
         let mut filter = true;
-        let mut max_slope = 75f64;
-        let mut last_returns = false;
+        let max_slope = parsed.get_f64("max_slope").unwrap_or(75f64);
+        let last_returns = parsed.get_bool("last_returns");
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-radius" {
-                search_radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_iter" {
-                num_iter = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_samples" {
-                num_samples = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-threshold" {
-                threshold = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-model_size" {
-                acceptable_model_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_slope" {
-                max_slope = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-classify" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    filter = false;
-                }
-            } else if flag_val == "-last_returns" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    last_returns = true;
-                }
-            }
+        if parsed.get_bool("classify") {
+            filter = false;
         }
 
         if verbose {
@@ -360,13 +254,8 @@ impl WhiteboxTool for LidarRansacPlanes {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -421,10 +310,11 @@ impl WhiteboxTool for LidarRansacPlanes {
             if (!last_returns || (last_returns && pd.is_late_return())) && !pd.is_classified_noise() {
                 frs.insert(p.x, p.y, p.z, i);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Adding points to search tree: {}%", progress);
+                    reporter.progress("Adding points to search tree", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -447,14 +337,14 @@ impl WhiteboxTool for LidarRansacPlanes {
         let frs = Arc::new(frs); // wrap FRS in an Arc
                                  // let kdtree = Arc::new(kdtree);
         let input = Arc::new(input); // wrap input in an Arc
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let frs = frs.clone();
             // let kdtree = kdtree.clone();
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut n: usize;
                 // let mut p1: PointData;
                 // let mut p2: PointData;
@@ -544,7 +434,9 @@ impl WhiteboxTool for LidarRansacPlanes {
                             model_points.clear();
                         }
                     }
-                    tx.send(model_points).unwrap();
+                    if tx.send(model_points).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -557,10 +449,11 @@ impl WhiteboxTool for LidarRansacPlanes {
             for index in model_points {
                 is_a_planar_surface[index] = true;
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -577,10 +470,11 @@ impl WhiteboxTool for LidarRansacPlanes {
                 } else {
                     num_points_filtered += 1;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -721,10 +615,11 @@ impl WhiteboxTool for LidarRansacPlanes {
                     }
                 }
                 output.add_point_record(pr2);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress as usize);
                         old_progress = progress;
                     }
                 }
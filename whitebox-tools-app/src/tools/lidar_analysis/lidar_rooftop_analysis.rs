@@ -15,14 +15,13 @@ use crate::tools::*;
 use whitebox_vector::*;
 use kdtree::distance::squared_euclidean;
 use kdtree::KdTree;
-use num_cpus;
 use rand::seq::SliceRandom;
 use rstar::RTree;
 use std::io::{Error, ErrorKind};
 use std::ops::AddAssign;
 use std::path;
 use std::sync::{mpsc, Arc};
-use std::thread;
+use whitebox_common::utils::spawn;
 use std::{env, fs};
 const EPSILON: f64 = std::f64::EPSILON;
 
@@ -57,7 +56,7 @@ impl LidarRooftopAnalysis {
 
         parameters.push(ToolParameter {
             name: "Input Building Footprint Polygon File".to_owned(),
-            flags: vec!["--buildings".to_owned()],
+            flags: vec!["--building".to_owned(), "--buildings".to_owned()],
             description: "Input vector build footprint polygons file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
                 VectorGeometryType::Polygon,
@@ -128,7 +127,7 @@ impl LidarRooftopAnalysis {
             flags: vec!["--model_size".to_owned()],
             description: "Acceptable model size, in points.".to_owned(),
             parameter_type: ParameterType::Integer,
-            default_value: Some("15".to_owned()),
+            default_value: Some("30".to_owned()),
             optional: true,
         });
 
@@ -146,7 +145,7 @@ impl LidarRooftopAnalysis {
             flags: vec!["--norm_diff".to_owned()],
             description: "Maximum difference in normal vectors, in degrees.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("10.0".to_owned()),
+            default_value: Some("2.0".to_owned()),
             optional: true,
         });
 
@@ -190,7 +189,7 @@ impl LidarRooftopAnalysis {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='data.las' --polygons='buildings.shp' -o='rooftops.shp' --radius=10.0 --num_iter=10 --num_samples=5 --threshold=0.25 --max_slope=70.0", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i='data.las' --buildings='buildings.shp' -o='rooftops.shp' --radius=10.0 --num_iter=10 --num_samples=5 --threshold=0.25 --max_slope=70.0", short_exe, name).replace("*", &sep);
 
         LidarRooftopAnalysis {
             name: name,
@@ -235,185 +234,28 @@ impl WhiteboxTool for LidarRooftopAnalysis {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut polygons_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut polygons_file = parsed.get_string("buildings").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         // let mut output_lidar_file = String::new();
-        let mut search_radius = 2f64;
-        let mut num_iter = 50;
-        let mut num_samples = 10;
-        let mut threshold = 0.15;
-        let mut acceptable_model_size = 30;
+        let search_radius = parsed.get_f64("radius").unwrap_or(2f64);
+        let num_iter = parsed.get_usize("num_iter")?.unwrap_or(50);
+        let mut num_samples = parsed.get_usize("num_samples")?.unwrap_or(10);
+        let threshold = parsed.get_f64("threshold").unwrap_or(0.15);
+        let mut acceptable_model_size = parsed.get_usize("model_size")?.unwrap_or(30);
         let mut max_slope = 65f64;
-        let mut max_norm_diff = 2f64;
-        let mut max_z_diff = 1f64;
-        let mut azimuth = 180.0f64;
-        let mut altitude = 30.0f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-building" || flag_val == "-buildings" {
-                polygons_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            // } else if flag_val == "-output_lidar" {
-            //     output_lidar_file = if keyval {
-            //         vec[1].to_string()
-            //     } else {
-            //         args[i + 1].to_string()
-            //     };
-            } else if flag_val == "-radius" {
-                search_radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_iter" {
-                num_iter = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_samples" {
-                num_samples = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-threshold" {
-                threshold = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-model_size" {
-                acceptable_model_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_slope" {
-                max_slope = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if max_slope < 5f64 {
-                    max_slope = 5f64;
-                }
-            } else if flag_val == "-norm_diff" {
-                max_norm_diff = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-maxzdiff" {
-                max_z_diff = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-azimuth" {
-                if keyval {
-                    azimuth = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    azimuth = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-altitude" {
-                if keyval {
-                    altitude = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    altitude = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
+        let mut max_norm_diff = parsed.get_f64("norm_diff").unwrap_or(2f64);
+        let max_z_diff = parsed.get_f64("maxzdiff").unwrap_or(1f64);
+        let mut azimuth = parsed.get_f64("azimuth").unwrap_or(180.0f64);
+        let mut altitude = parsed.get_f64("altitude").unwrap_or(30.0f64);
+
+        if let Some(value) = parsed.get_f64("max_slope") {
+            max_slope = value;
+            if max_slope < 5f64 {
+                max_slope = 5f64;
             }
         }
 
@@ -428,8 +270,6 @@ impl WhiteboxTool for LidarRooftopAnalysis {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: i32;
         let mut old_progress: i32 = -1;
 
@@ -441,9 +281,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
         // if !input_file.contains(&sep) && !input_file.contains("/") {
         //     input_file = format!("{}{}", working_directory, input_file);
         // }
-        if !polygons_file.contains(&sep) && !polygons_file.contains("/") {
-            polygons_file = format!("{}{}", working_directory, polygons_file);
-        }
+        polygons_file = resolve_input_path(&polygons_file, working_directory)?;
         // if !output_file.contains(&sep) && !output_file.contains("/") {
         //     output_file = format!("{}{}", working_directory, output_file);
         // }
@@ -510,9 +348,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                 ));
             }
         } else {
-            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_input_path(&input_file, working_directory)?;
             inputs.push(input_file.clone());
             if output_file.is_empty() {
                 output_file = input_file
@@ -522,9 +358,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                     .replace(".laz", ".shp")
                     .replace(".zlidar", ".shp");
             }
-            if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
         }
 
         let polygons =
@@ -544,6 +378,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
         // place the axis-aligned bounding boxes of each of the polygons into a vector
         let mut building_aabb = Vec::with_capacity(num_records);
         for record_num in 0..polygons.num_records {
+            reporter.check_cancelled()?;
             let record = polygons.get_record(record_num);
             if record.shape_type != ShapeType::Null {
                 building_aabb.push(RectangleWithData::new(
@@ -580,7 +415,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
             FixedRadiusSearch3D::new(search_radius, DistanceMetric::SquaredEuclidean);
         let mut point_num = 0usize;
         let mut projection = String::new();
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let mut file_num = 0;
         for input_file in inputs {
             match LasFile::new(&input_file, "r") {
@@ -592,12 +427,14 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                     let n_points = input.header.number_of_points as usize;
                     let input = Arc::new(input);
                     let (tx, rx) = mpsc::channel();
+                    let token = reporter.cancellation_token();
                     for tid in 0..num_procs {
                         let input = input.clone();
                         let building_tree = building_tree.clone();
                         let polygons = polygons.clone();
                         let tx = tx.clone();
-                        thread::spawn(move || {
+                        let token = token.clone();
+                        spawn(move || {
                             let mut progress: usize;
                             let mut old_progress = 1usize;
                             let mut pd: PointData;
@@ -612,6 +449,9 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                             for point_num in
                                 (0..n_points).filter(|point_num| point_num % num_procs == tid)
                             {
+                                if token.is_cancelled() {
+                                    break;
+                                }
                                 // p = input.get_point_info(point_num);
                                 p = input.get_transformed_coords(point_num);
                                 pd = input[point_num];
@@ -682,18 +522,16 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                                     progress =
                                         (100.0_f64 * point_num as f64 / n_points as f64) as usize;
                                     if progress != old_progress {
-                                        println!("Progress: {}%", progress);
+                                        if tx.send(WorkerMessage::Progress("Progress", progress)).is_err() {
+                                            return;
+                                        }
                                         old_progress = progress;
                                     }
                                 }
                             }
-                            match tx.send(building_points) {
-                                Ok(_) => {} // do nothing
-                                Err(_) => panic!(
-                                    "Error performing clipping operation on file: {}.",
-                                    input.get_short_filename()
-                                ),
-                            };
+                            if tx.send(WorkerMessage::Data(building_points)).is_err() {
+                                return;
+                            }
                         });
                     }
 
@@ -701,12 +539,13 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                     let mut building = vec![0; n_points];
                     for _ in 0..num_procs {
                         let in_building_points =
-                            rx.recv().expect("Error receiving data from thread.");
+                            recv_data(&rx, reporter).expect("Error receiving data from thread.");
                         for p in in_building_points {
                             point_in_building[p.0] = true;
                             building[p.0] = p.1;
                         }
                     }
+                    reporter.check_cancelled()?;
 
                     for p in 0..n_points {
                         if point_in_building[p] {
@@ -722,7 +561,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                         if num_files == 1 && verbose {
                             progress = (100.0_f64 * p as f64 / n_points as f64) as i32;
                             if progress != old_progress {
-                                println!("Progress: {}%", progress);
+                                reporter.progress("Progress", progress as usize);
                                 old_progress = progress;
                             }
                         }
@@ -732,7 +571,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                         file_num += 1;
                         progress = (100.0_f64 * file_num as f64 / num_files as f64) as i32;
                         if progress != old_progress {
-                            println!("Progress ({} of {}): {}%", file_num, num_files, progress);
+                            reporter.progress(&format!("Progress ({} of {})", file_num, num_files), progress as usize);
                             old_progress = progress;
                         }
                     }
@@ -745,7 +584,6 @@ impl WhiteboxTool for LidarRooftopAnalysis {
             };
         }
 
-
         ////////////
         // RANSAC //
         ////////////
@@ -765,7 +603,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
         // let las_points = Arc::new(las_points);
         let las_points_transformed = Arc::new(las_points_transformed);
         let building_num = Arc::new(building_num);
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let frs = frs.clone();
@@ -773,7 +611,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
             let las_points_transformed = las_points_transformed.clone();
             let building_num = building_num.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut n: usize;
                 // let mut p1: PointData;
                 // let mut p2: PointData;
@@ -858,7 +696,9 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                             model_points.clear();
                         }
                     }
-                    tx.send((best_model, min_rmse, model_points)).unwrap();
+                    if tx.send((best_model, min_rmse, model_points)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -875,10 +715,11 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -910,6 +751,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
 
         // all non-planar points belong to the same segment
         for i in 0..n_points {
+            reporter.check_cancelled()?;
             if model_rmse[i] == f64::MAX {
                 segment_id[i] = current_segment;
                 solved_points += 1;
@@ -935,10 +777,11 @@ impl WhiteboxTool for LidarRooftopAnalysis {
 
             while !stack.is_empty() {
                 solved_points += 1;
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100f64 * solved_points as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Segmenting the point cloud: {}%", progress);
+                        reporter.progress("Segmenting the point cloud", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -994,6 +837,7 @@ impl WhiteboxTool for LidarRooftopAnalysis {
 
         let mut seg_num: usize;
         for point_id in 0..n_points {
+            reporter.check_cancelled()?;
             seg_num = segment_id[point_id];
             segment_size[seg_num] += 1;
             // p = las_points[point_id].get_point_data();
@@ -1630,16 +1474,16 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                 }
                 // */
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * building_id as f64 / num_buildings as f64) as i32;
                 if progress != old_progress {
-                    println!("Creating roof segment polygons: {}%", progress);
+                    reporter.progress("Creating roof segment polygons", progress as usize);
                     old_progress = progress;
                 }
             }
         }
 
-
         /*
         let mut clrs: Vec<(u16, u16, u16)> = Vec::new();
         let mut rng = rand::thread_rng();
@@ -1673,10 +1517,11 @@ impl WhiteboxTool for LidarRooftopAnalysis {
                     colour_data: rgb,
                 };
                 output_lidar.add_point_record(lpr);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress);
                         old_progress = progress;
                     }
                 }
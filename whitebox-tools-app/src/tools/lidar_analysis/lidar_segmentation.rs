@@ -15,14 +15,13 @@ use crate::tools::*;
 use rand::seq::SliceRandom;
 // use kdtree::distance::squared_euclidean;
 // use kdtree::KdTree;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to segment a LiDAR point cloud based on differences in the orientation of fitted planar
 /// surfaces and point proximity. The algorithm begins by attempting to fit planar surfaces to all of the points within
@@ -149,7 +148,7 @@ impl LidarSegmentation {
             flags: vec!["--model_size".to_owned()],
             description: "Acceptable model size.".to_owned(),
             parameter_type: ParameterType::Integer,
-            default_value: Some("15".to_owned()),
+            default_value: Some("30".to_owned()),
             optional: true,
         });
 
@@ -158,7 +157,7 @@ impl LidarSegmentation {
             flags: vec!["--max_slope".to_owned()],
             description: "Maximum planar slope.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("80.0".to_owned()),
+            default_value: Some("75.0".to_owned()),
             optional: true,
         });
 
@@ -167,7 +166,7 @@ impl LidarSegmentation {
             flags: vec!["--norm_diff".to_owned()],
             description: "Maximum difference in normal vectors, in degrees.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("10.0".to_owned()),
+            default_value: Some("2.0".to_owned()),
             optional: true,
         });
 
@@ -263,156 +262,27 @@ impl WhiteboxTool for LidarSegmentation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut search_radius = 2f64;
-        let mut num_iter = 50;
-        let mut num_samples = 10;
-        let mut threshold = 0.15;
-        let mut acceptable_model_size = 30;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let search_radius = parsed.get_f64("radius").unwrap_or(2f64);
+        let num_iter = parsed.get_usize("num_iter")?.unwrap_or(50);
+        let mut num_samples = parsed.get_usize("num_samples")?.unwrap_or(10);
+        let threshold = parsed.get_f64("threshold").unwrap_or(0.15);
+        let mut acceptable_model_size = parsed.get_usize("model_size")?.unwrap_or(30);
         let mut max_slope = 75f64;
-        let mut max_norm_diff = 2f64;
-        let mut max_z_diff = 1f64;
-        let mut dont_cross_class_boundaries = false;
-        let mut ground_class = false;
+        let mut max_norm_diff = parsed.get_f64("norm_diff").unwrap_or(2f64);
+        let max_z_diff = parsed.get_f64("maxzdiff").unwrap_or(1f64);
+        let dont_cross_class_boundaries = parsed.get_bool("classes");
+        let ground_class = parsed.get_bool("ground");
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-radius" {
-                search_radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_iter" {
-                num_iter = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_samples" {
-                num_samples = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-threshold" {
-                threshold = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-model_size" {
-                acceptable_model_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_slope" {
-                max_slope = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if max_slope < 5f64 {
-                    max_slope = 5f64;
-                }
-            } else if flag_val == "-norm_diff" {
-                max_norm_diff = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-maxzdiff" {
-                max_z_diff = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-classes" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    dont_cross_class_boundaries = true;
-                }
-            } else if flag_val == "-ground" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    ground_class = true;
-                }
+        if let Some(value) = parsed.get_f64("max_slope") {
+            max_slope = value;
+            if max_slope < 5f64 {
+                max_slope = 5f64;
             }
         }
 
@@ -427,13 +297,8 @@ impl WhiteboxTool for LidarSegmentation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -488,10 +353,11 @@ impl WhiteboxTool for LidarSegmentation {
             if !pd.withheld() && !pd.is_classified_noise() {
                 frs.insert(p.x, p.y, p.z, i);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Adding points to search tree: {}%", progress);
+                    reporter.progress("Adding points to search tree", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -500,14 +366,14 @@ impl WhiteboxTool for LidarSegmentation {
         let frs = Arc::new(frs); // wrap FRS in an Arc
                                  // let kdtree = Arc::new(kdtree);
         let input = Arc::new(input); // wrap input in an Arc
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let frs = frs.clone();
             // let kdtree = kdtree.clone();
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut n: usize;
                 // let mut p1: PointData;
                 // let mut p2: PointData;
@@ -590,10 +456,14 @@ impl WhiteboxTool for LidarSegmentation {
                                 model_points.clear();
                             }
                         }
-                        tx.send((best_model, min_rmse, model_points)).unwrap();
+                        if tx.send((best_model, min_rmse, model_points)).is_err() {
+                            return;
+                        }
                     } else {
                         let model_points: Vec<usize> = vec![];
-                        tx.send((best_model, f64::MAX, model_points)).unwrap();
+                        if tx.send((best_model, f64::MAX, model_points)).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -611,10 +481,11 @@ impl WhiteboxTool for LidarSegmentation {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -663,10 +534,11 @@ impl WhiteboxTool for LidarSegmentation {
 
             while !stack.is_empty() {
                 solved_points += 1;
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100f64 * solved_points as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Segmenting the point cloud: {}%", progress);
+                        reporter.progress("Segmenting the point cloud", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -735,6 +607,7 @@ impl WhiteboxTool for LidarSegmentation {
         let mut largest_size = 0usize;
         let mut largest_segment = 0usize;
         for point_num in 0..n_points {
+            reporter.check_cancelled()?;
             seg_val = segment_id[point_num];
             segment_size[seg_val] += 1;
             if segment_size[seg_val] > largest_size {
@@ -785,10 +658,11 @@ impl WhiteboxTool for LidarSegmentation {
                 colour_data: rgb,
             };
             output.add_point_record(lpr);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Saving data: {}%", progress);
+                    reporter.progress("Saving data", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -11,15 +11,14 @@ use whitebox_lidar::*;
 use crate::na;
 use whitebox_common::structures::{DistanceMetric, FixedRadiusSearch2D, FixedRadiusSearch3D};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::NEG_INFINITY;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 pub struct LidarSegmentationBasedFilter {
     name: String,
@@ -156,100 +155,21 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut search_radius = 5f64;
-        let mut max_norm_diff = 2f64;
-        let mut max_z_diff = 1f64;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let search_radius = parsed.get_f64("radius").unwrap_or(5f64);
+        let mut max_norm_diff = parsed.get_f64("norm_diff").unwrap_or(2f64);
+        let max_z_diff = parsed.get_f64("maxzdiff").unwrap_or(1f64);
         let ground_class_value = 2u8;
         let otp_class_value = 1u8;
         let mut filter = true;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-dist" || flag_val == "-radius" {
-                if keyval {
-                    search_radius = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    search_radius = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-norm_diff" {
-                if keyval {
-                    max_norm_diff = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    max_norm_diff = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-maxzdiff" {
-                if keyval {
-                    max_z_diff = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    max_z_diff = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-classify" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    filter = false;
-                }
-                // } else if flag_val == "-groundclass" {
-                //     filter = false;
-                //     if keyval {
-                //         ground_class_value = vec[1].to_string().parse::<u8>().unwrap();
-                //     } else {
-                //         ground_class_value = args[i+1].to_string().parse::<u8>().unwrap();
-                //     }
-                // } else if flag_val == "-otp_class_value" {
-                //     filter = false;
-                //     if keyval {
-                //         otp_class_value = vec[1].to_string().parse::<u8>().unwrap();
-                //     } else {
-                //         otp_class_value = args[i+1].to_string().parse::<u8>().unwrap();
-                //     }
-            }
+        if parsed.get_bool("classify") {
+            filter = false;
         }
 
         if verbose {
@@ -263,13 +183,8 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -297,7 +212,7 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
 
         let mut progress: i32;
         let mut old_progress: i32 = -1;
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
 
         // We'll eventually need the ability to do fixed radius searches around
         // each point in the point cloud in both 2D and 3D.
@@ -307,10 +222,11 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
             // let p: PointData = input.get_point_info(i);
             let p = input.get_transformed_coords(i);
             frs2d.insert(p.x, p.y, i);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Binning points in 2D: {}%", progress);
+                    reporter.progress("Binning points in 2D", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -332,7 +248,7 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
             let frs = frs.clone();
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut index_n: usize;
                 let mut z_n: f64;
                 let mut min_z: f64;
@@ -348,7 +264,9 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
                             min_z = z_n;
                         }
                     }
-                    tx.send((point_num, min_z)).unwrap();
+                    if tx.send((point_num, min_z)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -356,10 +274,11 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
         for point_num in 0..n_points {
             let data = rx.recv().expect("Error receiving data from thread.");
             neighbourhood_min[data.0] = data.1;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Erosion: {}%", progress);
+                    reporter.progress("Erosion", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -372,7 +291,7 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
             let input = input.clone();
             let neighbourhood_min = neighbourhood_min.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut index_n: usize;
                 let mut z_n: f64;
                 let mut max_z: f64;
@@ -388,7 +307,9 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
                             max_z = z_n;
                         }
                     }
-                    tx.send((point_num, max_z)).unwrap();
+                    if tx.send((point_num, max_z)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -397,10 +318,11 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
             let data = rx.recv().expect("Error receiving data from thread.");
             let z = input.get_transformed_coords(data.0).z;
             residuals[data.0] = z - data.1;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Dilation: {}%", progress);
+                    reporter.progress("Dilation", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -419,10 +341,11 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
             let p = input.get_transformed_coords(point_num);
             frs3d.insert(p.x, p.y, residuals[point_num], point_num);
             // using the top-hat transformed data to calculate normals
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Binning points in 3D: {}%", progress);
+                    reporter.progress("Binning points in 3D", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -436,7 +359,7 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
             let input = input.clone();
             let residuals = residuals.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut index_n: usize;
                 for point_num in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
                     // let p: PointData = input.get_point_info(point_num);
@@ -449,7 +372,9 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
                         let p2 = input.get_transformed_coords(index_n);
                         data.push(Vector3::new(p2.x, p2.y, residuals[index_n]));
                     }
-                    tx.send((point_num, plane_from_points(&data))).unwrap();
+                    if tx.send((point_num, plane_from_points(&data))).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -464,10 +389,11 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
                 is_ground_point[data.0] = true;
                 stack.push(data.0);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Calculating point normals: {}%", progress);
+                    reporter.progress("Calculating point normals", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -511,10 +437,11 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100f64 * (1f64 - stack.len() as f64 / num_points)) as i32;
                 if progress != old_progress {
-                    println!("Segmenting the point cloud: {}%", progress);
+                    reporter.progress("Segmenting the point cloud", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -534,10 +461,11 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
                 } else {
                     num_points_filtered += 1;
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -678,10 +606,11 @@ impl WhiteboxTool for LidarSegmentationBasedFilter {
                     }
                 }
                 output.add_point_record(pr2);
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * point_num as f64 / num_points) as i32;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -62,16 +62,16 @@ impl LidarThin {
                 "The size of the square area used to evaluate nearby points in the LiDAR data."
                     .to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("2.0".to_owned()),
+            default_value: Some("1.0".to_owned()),
             optional: true,
         });
 
         parameters.push(ToolParameter{
             name: "Point Selection Method".to_string(), 
             flags: vec!["--method".to_string()], 
-            description: "Point selection method; options are 'first', 'last', 'lowest' (default), 'highest', 'nearest'.".to_string(),
+            description: "Point selection method; options are 'first' (default), 'last', 'lowest', 'highest', 'nearest'.".to_string(),
             parameter_type: ParameterType::OptionList(vec!["first".to_string(), "last".to_string(), "lowest".to_string(), "highest".to_string(), "nearest".to_string()]),
-            default_value: Some("lowest".to_string()),
+            default_value: Some("first".to_string()),
             optional: true
         });
 
@@ -97,7 +97,7 @@ impl LidarThin {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=file.las -o=outfile.las --resolution=2.0, --method=first --save_filtered", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=file.las -o=outfile.las --resolution=2.0 --method=first --save_filtered", short_exe, name).replace("*", &sep);
 
         LidarThin {
             name: name,
@@ -149,81 +149,28 @@ impl WhiteboxTool for LidarThin {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut grid_res: f64 = 1.0;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
         let mut method: String = "first".to_string();
-        let mut save_filtered = false;
+        let save_filtered = parsed.get_bool("save_filtered");
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-method" {
-                method = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                method = method.to_lowercase();
-            } else if flag_val == "-save_filtered" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    save_filtered = true;
-                }
-            }
+        if let Some(value) = parsed.get_string("method") {
+            method = value.to_string();
+            method = method.to_lowercase();
         }
 
         let start = Instant::now();
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -283,10 +230,11 @@ impl WhiteboxTool for LidarThin {
                         pt_id.set_value(row, col, i);
                         filtered[i] = false;
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * i as f64 / num_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -307,10 +255,11 @@ impl WhiteboxTool for LidarThin {
                         pt_id.set_value(row, col, i);
                         filtered[prev_id] = true;
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * i as f64 / num_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -333,10 +282,11 @@ impl WhiteboxTool for LidarThin {
                     } else {
                         filtered[i] = true;
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * i as f64 / num_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -359,10 +309,11 @@ impl WhiteboxTool for LidarThin {
                     } else {
                         filtered[i] = true;
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * i as f64 / num_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -396,10 +347,11 @@ impl WhiteboxTool for LidarThin {
                     } else {
                         filtered[i] = true;
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * i as f64 / num_points) as usize;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress);
                             old_progress = progress;
                         }
                     }
@@ -427,10 +379,11 @@ impl WhiteboxTool for LidarThin {
                 if !filtered[i] {
                     output.add_point_record(input.get_record(i));
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as usize;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress);
                         old_progress = progress;
                     }
                 }
@@ -451,10 +404,11 @@ impl WhiteboxTool for LidarThin {
                 } else {
                     filtered_output.add_point_record(input.get_record(i));
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * i as f64 / num_points) as usize;
                     if progress != old_progress {
-                        println!("Saving data: {}%", progress);
+                        reporter.progress("Saving data", progress);
                         old_progress = progress;
                     }
                 }
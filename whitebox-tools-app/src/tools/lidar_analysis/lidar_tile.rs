@@ -50,7 +50,7 @@ impl LidarTile {
 
         parameters.push(ToolParameter {
             name: "Tile Width".to_owned(),
-            flags: vec!["--width".to_owned()],
+            flags: vec!["--width_x".to_owned(), "--width".to_owned()],
             description: "Width of tiles in the X dimension; default 1000.0.".to_owned(),
             parameter_type: ParameterType::Float,
             default_value: Some("1000.0".to_owned()),
@@ -59,7 +59,7 @@ impl LidarTile {
 
         parameters.push(ToolParameter {
             name: "Tile Height".to_owned(),
-            flags: vec!["--height".to_owned()],
+            flags: vec!["--width_y".to_owned(), "--height".to_owned()],
             description: "Height of tiles in the Y dimension.".to_owned(),
             parameter_type: ParameterType::Float,
             default_value: Some("1000.0".to_owned()),
@@ -159,101 +159,17 @@ impl WhiteboxTool for LidarTile {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = String::new();
-        let mut width_x = 1000.0;
-        let mut width_y = 1000.0;
-        let mut origin_x = 0.0;
-        let mut origin_y = 0.0;
-        let mut min_points = 2;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let width_x = parsed.get_f64("width").unwrap_or(1000.0);
+        let width_y = parsed.get_f64("height").unwrap_or(1000.0);
+        let origin_x = parsed.get_f64("origin_x").unwrap_or(0.0);
+        let origin_y = parsed.get_f64("origin_y").unwrap_or(0.0);
+        let mut min_points = parsed.get_usize("min_points")?.unwrap_or(2);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-width_x" || flag_val == "-width" {
-                if keyval {
-                    width_x = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    width_x = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-width_y" || flag_val == "-height" {
-                if keyval {
-                    width_y = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    width_y = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-origin_x" {
-                if keyval {
-                    origin_x = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    origin_x = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-origin_y" {
-                if keyval {
-                    origin_y = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    origin_y = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-min_points" {
-                if keyval {
-                    min_points = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    min_points = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -266,8 +182,6 @@ impl WhiteboxTool for LidarTile {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = std::path::MAIN_SEPARATOR;
-
         if min_points < 2 {
             min_points = 2;
         }
@@ -276,9 +190,7 @@ impl WhiteboxTool for LidarTile {
             panic!("ERROR: The grid cell width must be greater than zero.");
         }
 
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
         if verbose {
             println!("Performing analysis...");
@@ -324,10 +236,11 @@ impl WhiteboxTool for LidarTile {
             row = (((p.y - origin_y) / width_y) - start_y_grid).floor() as usize; // relative to the grid edge
             tile_data[i] = row * cols + col;
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 3): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 3)", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -345,10 +258,11 @@ impl WhiteboxTool for LidarTile {
             if first_point_num[tile_data[i]] == n_points_plus_one {
                 first_point_num[tile_data[i]] = i;
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 3): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 3)", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -422,10 +336,11 @@ impl WhiteboxTool for LidarTile {
                 num_tiles_created += 1;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tile_num as f64 / (num_tiles - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress (Loop 3 of 3): {}%", progress);
+                    reporter.progress("Progress (Loop 3 of 3)", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -12,14 +12,13 @@ use whitebox_common::structures::Point2D;
 use crate::tools::*;
 use whitebox_vector::ShapefileGeometry;
 use whitebox_vector::*;
-use num_cpus;
 use std::env;
 use std::fs;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to create a vector polygon of the bounding box or convex hull of a LiDAR point cloud (i.e. LAS file).
 /// If the user specified an input file (`--input`) and output file (`--output`), the tool will calculate the footprint,
@@ -80,7 +79,7 @@ impl LidarTileFootprint {
 
         parameters.push(ToolParameter {
             name: "Create Convex Hull Around Points".to_owned(),
-            flags: vec!["--hull".to_owned()],
+            flags: vec!["--convex_hull".to_owned(), "--hull".to_owned()],
             description: "Identify the convex hull around points.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_string()),
@@ -156,54 +155,18 @@ impl WhiteboxTool for LidarTileFootprint {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut is_convex_hull = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let is_convex_hull = parsed.get_bool("hull");
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-hull" || flag_val == "-convex_hull" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    is_convex_hull = true;
-                }
-            }
-        }
-
-        let sep: String = path::MAIN_SEPARATOR.to_string();
 
         let start = Instant::now();
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
         let mut inputs = vec![];
         if input_file.is_empty() {
             if working_directory.is_empty() {
@@ -244,9 +207,7 @@ impl WhiteboxTool for LidarTileFootprint {
                 ));
             }
         } else {
-            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_input_path(&input_file, working_directory)?;
             inputs.push(input_file.clone());
         }
 
@@ -265,19 +226,15 @@ impl WhiteboxTool for LidarTileFootprint {
         let tile_list = Arc::new(Mutex::new(0..num_tiles));
         let wkt = Arc::new(Mutex::new(String::new()));
         let inputs = Arc::new(inputs);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = channel();
         for _ in 0..num_procs {
             let inputs = inputs.clone();
             let tile_list = tile_list.clone();
             let wkt = wkt.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut tile = 0;
                 while tile < num_tiles {
                     // Get the next tile up for processing
@@ -335,26 +292,28 @@ impl WhiteboxTool for LidarTileFootprint {
                                     *data = input.get_wkt();
                                 }
                                 // send the data to the main thread to be output
-                                tx.send((
+                                if tx.send((
                                     hull_points,
                                     short_filename,
                                     n_points,
                                     input.header.min_z,
                                     input.header.max_z,
                                     input.get_wkt(),
-                                ))
-                                .unwrap();
+                                )).is_err() {
+                                    return;
+                                }
                             }
                             Err(err) => {
-                                tx.send((
+                                if tx.send((
                                     vec![],
                                     format!("Error reading file {}:\n{}", input_file, err),
                                     0,
                                     0f64,
                                     0f64,
                                     "".to_string(),
-                                ))
-                                .unwrap();
+                                )).is_err() {
+                                    return;
+                                }
                             }
                         };
                     } else {
@@ -374,26 +333,28 @@ impl WhiteboxTool for LidarTileFootprint {
                                     );
                                 }
 
-                                tx.send((
+                                if tx.send((
                                     bounding_points,
                                     short_filename,
                                     header.get_number_of_points() as usize,
                                     header.min_z,
                                     header.max_z,
                                     "".to_string(),
-                                ))
-                                .unwrap();
+                                )).is_err() {
+                                    return;
+                                }
                             }
                             Err(err) => {
-                                tx.send((
+                                if tx.send((
                                     vec![],
                                     format!("Error reading file {}:\n{}", input_file, err),
                                     0,
                                     0f64,
                                     0f64,
                                     "".to_string(),
-                                ))
-                                .unwrap();
+                                )).is_err() {
+                                    return;
+                                }
                             }
                         }
                     }
@@ -462,10 +423,11 @@ impl WhiteboxTool for LidarTileFootprint {
                 }
                 Err(val) => println!("Error: {:?}", val),
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tile as f64 / (num_tiles - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress ({} of {}): {}%", (tile + 1), num_tiles, progress);
+                    reporter.progress(&format!("Progress ({} of {})", (tile + 1), num_tiles), progress as usize);
                     old_progress = progress;
                 }
             }
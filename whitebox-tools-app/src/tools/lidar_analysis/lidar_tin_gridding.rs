@@ -2,7 +2,7 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 21/09/2018
-Last Modified: 25/05/2020
+Last Modified: 16/10/2026
 License: MIT
 */
 
@@ -11,15 +11,15 @@ use whitebox_common::algorithms::{point_in_poly, triangulate};
 use whitebox_lidar::*;
 use crate::na;
 use whitebox_raster::*;
-use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+use whitebox_common::spatial_ref_system::CoordinateReferenceSystem;
 use whitebox_common::structures::{BoundingBox, Point2D};
 use crate::tools::*;
-use num_cpus;
 use std::io::{Error, ErrorKind};
 use std::sync::mpsc;
 // use std::sync::{Arc, Mutex};
 use std::sync::Arc;
-use std::{env, f64, fs, path, thread};
+use std::{env, f64, fs, path};
+use whitebox_common::utils::spawn;
 
 /// This tool creates a raster grid based on a Delaunay triangular irregular network (TIN) fitted to LiDAR points.
 /// The output grid can be based on any of the stored LiDAR point parameters (`--parameter`), including elevation
@@ -140,7 +140,7 @@ impl LidarTINGridding {
             flags: vec!["--exclude_cls".to_owned()], 
             description: "Optional exclude classes from interpolation; Valid class values range from 0 to 18, based on LAS specifications. Example, --exclude_cls='3,4,5,6,7,18'.".to_owned(),
             parameter_type: ParameterType::String,
-            default_value: Some("7,18".to_owned()),
+            default_value: None,
             optional: true
         });
 
@@ -236,151 +236,66 @@ impl WhiteboxTool for LidarTINGridding {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
         let mut interp_parameter = "elevation".to_string();
         let mut interp_parameter_is_rgb = false;
-        let mut return_type = "all".to_string();
-        let mut grid_res: f64 = 1.0;
+        let return_type = parsed.get_string("returns").unwrap_or("all".to_string());
+        let grid_res: f64 = parsed.get_f64("resolution").unwrap_or(1.0);
         let mut include_class_vals = vec![true; 256];
         let mut exclude_cls_str = String::new();
-        let mut max_z = f64::INFINITY;
-        let mut min_z = f64::NEG_INFINITY;
+        let max_z = parsed.get_f64("maxz").unwrap_or(f64::INFINITY);
+        let min_z = parsed.get_f64("minz").unwrap_or(f64::NEG_INFINITY);
         let mut max_triangle_edge_length = f64::INFINITY;
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_string("parameter") {
+            interp_parameter = value.to_string().to_lowercase();
+            if interp_parameter == "rgb" {
+                interp_parameter_is_rgb = true;
+            }
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_string("exclude_cls") {
+            exclude_cls_str = value.to_string();
+            let mut cmd = exclude_cls_str.split(",");
+            let mut vec = cmd.collect::<Vec<&str>>();
+            if vec.len() == 1 {
+                cmd = exclude_cls_str.split(";");
+                vec = cmd.collect::<Vec<&str>>();
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-parameter" {
-                interp_parameter = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-                if interp_parameter == "rgb" {
-                    interp_parameter_is_rgb = true;
-                }
-            } else if flag_val == "-returns" {
-                return_type = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-resolution" {
-                grid_res = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-exclude_cls" {
-                exclude_cls_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                let mut cmd = exclude_cls_str.split(",");
-                let mut vec = cmd.collect::<Vec<&str>>();
-                if vec.len() == 1 {
-                    cmd = exclude_cls_str.split(";");
-                    vec = cmd.collect::<Vec<&str>>();
-                }
-                for value in vec {
-                    if !value.trim().is_empty() {
-                        if value.contains("-") {
-                            cmd = value.split("-");
-                            vec = cmd.collect::<Vec<&str>>();
-                            let c = vec[0].trim().parse::<usize>().unwrap();
-                            let d = vec[1].trim().parse::<usize>().unwrap();
-                            for e in c..=d {
-                                include_class_vals[e] = false;
-                            }
-                        } else if value.contains("...") {
-                            cmd = value.split("...");
-                            vec = cmd.collect::<Vec<&str>>();
-                            let c = vec[0].trim().parse::<usize>().unwrap();
-                            let d = vec[1].trim().parse::<usize>().unwrap();
-                            for e in c..=d {
-                                include_class_vals[e] = false;
-                            }
-                        } else {
-                            let c = value.trim().parse::<usize>().unwrap();
-                            include_class_vals[c] = false;
+            for value in vec {
+                if !value.trim().is_empty() {
+                    if value.contains("-") {
+                        cmd = value.split("-");
+                        vec = cmd.collect::<Vec<&str>>();
+                        let c = vec[0].trim().parse::<usize>().unwrap();
+                        let d = vec[1].trim().parse::<usize>().unwrap();
+                        for e in c..=d {
+                            include_class_vals[e] = false;
                         }
+                    } else if value.contains("...") {
+                        cmd = value.split("...");
+                        vec = cmd.collect::<Vec<&str>>();
+                        let c = vec[0].trim().parse::<usize>().unwrap();
+                        let d = vec[1].trim().parse::<usize>().unwrap();
+                        for e in c..=d {
+                            include_class_vals[e] = false;
+                        }
+                    } else {
+                        let c = value.trim().parse::<usize>().unwrap();
+                        include_class_vals[c] = false;
                     }
                 }
-            } else if flag_val == "-minz" {
-                min_z = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-maxz" {
-                max_z = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_triangle_edge_length" {
-                max_triangle_edge_length = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-
-                max_triangle_edge_length *= max_triangle_edge_length; // actually squared distance
             }
         }
+        if let Some(value) = parsed.get_f64("max_triangle_edge_length") {
+            max_triangle_edge_length = value;
+
+            max_triangle_edge_length *= max_triangle_edge_length; // actually squared distance
+        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -465,9 +380,7 @@ impl WhiteboxTool for LidarTINGridding {
                 ));
             }
         } else {
-            if !input_file.contains(path::MAIN_SEPARATOR) && !input_file.contains("/") {
-                input_file = format!("{}{}", working_directory, input_file);
-            }
+            input_file = resolve_path(&input_file, working_directory);
             inputs.push(input_file.clone());
             if output_file.is_empty() {
                 output_file = input_file
@@ -478,9 +391,7 @@ impl WhiteboxTool for LidarTINGridding {
                     .replace(".LAZ", ".tif")
                     .replace(".zlidar", ".tif");
             }
-            if !output_file.contains(path::MAIN_SEPARATOR) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
             outputs.push(output_file);
         }
 
@@ -509,11 +420,12 @@ impl WhiteboxTool for LidarTINGridding {
 
         let num_tiles = inputs.len();
         // let tile_list = Arc::new(Mutex::new(0..num_tiles));
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let inputs = Arc::new(inputs);
         let outputs = Arc::new(outputs);
         let bounding_boxes = Arc::new(bounding_boxes);
         // let num_procs2 = num_cpus::get() as isize;
+        let cancellation = reporter.cancellation_token();
         let (tx2, rx2) = mpsc::channel();
         for tid in 0..num_procs {
             let inputs = inputs.clone();
@@ -528,8 +440,12 @@ impl WhiteboxTool for LidarTINGridding {
             let exclude_cls_str = exclude_cls_str.clone();
             let include_class_vals = include_class_vals.clone();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            let cancellation = cancellation.clone();
+            spawn(move || {
                 for tile in (0..num_tiles).filter(|t| t % num_procs == tid) {
+                    if cancellation.is_cancelled() {
+                        break;
+                    }
                     // let mut tile = 0;
                     // while tile < num_tiles {
                     //     // Get the next tile up for interpolation
@@ -611,7 +527,9 @@ impl WhiteboxTool for LidarTINGridding {
                                                     progress =
                                                         (100.0_f64 * i as f64 / num_points) as i32;
                                                     if progress != old_progress {
-                                                        println!("Reading points: {}%", progress);
+                                                        if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                            return;
+                                                        }
                                                         old_progress = progress;
                                                     }
                                                 }
@@ -646,7 +564,9 @@ impl WhiteboxTool for LidarTINGridding {
                                                     progress =
                                                         (100.0_f64 * i as f64 / num_points) as i32;
                                                     if progress != old_progress {
-                                                        println!("Reading points: {}%", progress);
+                                                        if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                            return;
+                                                        }
                                                         old_progress = progress;
                                                     }
                                                 }
@@ -681,7 +601,9 @@ impl WhiteboxTool for LidarTINGridding {
                                                     progress =
                                                         (100.0_f64 * i as f64 / num_points) as i32;
                                                     if progress != old_progress {
-                                                        println!("Reading points: {}%", progress);
+                                                        if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                            return;
+                                                        }
                                                         old_progress = progress;
                                                     }
                                                 }
@@ -716,7 +638,9 @@ impl WhiteboxTool for LidarTINGridding {
                                                     progress =
                                                         (100.0_f64 * i as f64 / num_points) as i32;
                                                     if progress != old_progress {
-                                                        println!("Reading points: {}%", progress);
+                                                        if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                            return;
+                                                        }
                                                         old_progress = progress;
                                                     }
                                                 }
@@ -752,7 +676,9 @@ impl WhiteboxTool for LidarTINGridding {
                                                     progress =
                                                         (100.0_f64 * i as f64 / num_points) as i32;
                                                     if progress != old_progress {
-                                                        println!("Reading points: {}%", progress);
+                                                        if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                            return;
+                                                        }
                                                         old_progress = progress;
                                                     }
                                                 }
@@ -789,7 +715,9 @@ impl WhiteboxTool for LidarTINGridding {
                                                     progress =
                                                         (100.0_f64 * i as f64 / num_points) as i32;
                                                     if progress != old_progress {
-                                                        println!("Reading points: {}%", progress);
+                                                        if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                            return;
+                                                        }
                                                         old_progress = progress;
                                                     }
                                                 }
@@ -827,7 +755,9 @@ impl WhiteboxTool for LidarTINGridding {
                                                     progress =
                                                         (100.0_f64 * i as f64 / num_points) as i32;
                                                     if progress != old_progress {
-                                                        println!("Reading points: {}%", progress);
+                                                        if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                            return;
+                                                        }
                                                         old_progress = progress;
                                                     }
                                                 }
@@ -863,7 +793,9 @@ impl WhiteboxTool for LidarTINGridding {
                                                     progress =
                                                         (100.0_f64 * i as f64 / num_points) as i32;
                                                     if progress != old_progress {
-                                                        println!("Reading points: {}%", progress);
+                                                        if tx2.send(WorkerMessage::Progress("Reading points", progress as usize)).is_err() {
+                                                            return;
+                                                        }
                                                         old_progress = progress;
                                                     }
                                                 }
@@ -881,7 +813,9 @@ impl WhiteboxTool for LidarTINGridding {
                             "Warning: No eligible points found in {}",
                             inputs[tile].clone()
                         );
-                        tx2.send(tile).unwrap();
+                        if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                            return;
+                        }
                     } else {
                         let west: f64 = bounding_boxes[tile].min_x;
                         let north: f64 = bounding_boxes[tile].max_y;
@@ -907,8 +841,7 @@ impl WhiteboxTool for LidarTINGridding {
                         configs.nodata = nodata;
                         configs.data_type = DataType::F32;
                         configs.photometric_interp = PhotometricInterpretation::Continuous;
-                        configs.epsg_code = epsg_code;
-                        configs.projection = esri_wkt_from_epsg(epsg_code);
+                        configs.set_crs(&CoordinateReferenceSystem::from_epsg(epsg_code));
 
                         let mut output = Raster::initialize_using_config(&output_file, &configs);
                         if interp_parameter == "rgb" {
@@ -941,6 +874,9 @@ impl WhiteboxTool for LidarTINGridding {
                         let mut i: usize;
                         if !interp_parameter_is_rgb {
                             for triangle in 0..num_triangles {
+                                if cancellation.is_cancelled() {
+                                    break;
+                                }
                                 i = triangle * 3;
                                 p1 = result.triangles[i];
                                 p2 = result.triangles[i + 1];
@@ -994,8 +930,8 @@ impl WhiteboxTool for LidarTINGridding {
 
                                     for row in top_row..=bottom_row {
                                         for col in left_col..=right_col {
-                                            x = west + col as f64 * grid_res;
-                                            y = north - row as f64 * grid_res;
+                                            x = west + (col as f64 + 0.5) * grid_res; // cell centre
+                                            y = north - (row as f64 + 0.5) * grid_res;
                                             if point_in_poly(&Point2D::new(x, y), &tri_points) {
                                                 // calculate the z values
                                                 zn = -(norm.x * x + norm.y * y + k) / norm.z;
@@ -1009,7 +945,9 @@ impl WhiteboxTool for LidarTINGridding {
                                             / (num_triangles - 1) as f64)
                                             as i32;
                                         if progress != old_progress {
-                                            println!("Progress: {}%", progress);
+                                            if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                                return;
+                                            }
                                             old_progress = progress;
                                         }
                                     }
@@ -1024,6 +962,9 @@ impl WhiteboxTool for LidarTINGridding {
                             );
                             let (mut red, mut green, mut blue): (f64, f64, f64);
                             for triangle in 0..num_triangles {
+                                if cancellation.is_cancelled() {
+                                    break;
+                                }
                                 i = triangle * 3;
                                 p1 = result.triangles[i];
                                 p2 = result.triangles[i + 1];
@@ -1090,8 +1031,8 @@ impl WhiteboxTool for LidarTINGridding {
 
                                     for row in top_row..=bottom_row {
                                         for col in left_col..=right_col {
-                                            x = west + col as f64 * grid_res;
-                                            y = north - row as f64 * grid_res;
+                                            x = west + (col as f64 + 0.5) * grid_res; // cell centre
+                                            y = north - (row as f64 + 0.5) * grid_res;
                                             if point_in_poly(&Point2D::new(x, y), &tri_points) {
                                                 // calculate the colour values
                                                 red =
@@ -1115,7 +1056,9 @@ impl WhiteboxTool for LidarTINGridding {
                                             / (num_triangles - 1) as f64)
                                             as i32;
                                         if progress != old_progress {
-                                            println!("Progress: {}%", progress);
+                                            if tx2.send(WorkerMessage::Progress("Progress", progress as usize)).is_err() {
+                                                return;
+                                            }
                                             old_progress = progress;
                                         }
                                     }
@@ -1123,6 +1066,10 @@ impl WhiteboxTool for LidarTINGridding {
                             }
                         }
 
+                        if cancellation.is_cancelled() {
+                            break; // don't write the incomplete output
+                        }
+
                         let elapsed_time_run = get_formatted_elapsed_time(start_run);
                         output.add_metadata_entry(format!(
                             "Created by whitebox_tools\' {} tool",
@@ -1147,16 +1094,30 @@ impl WhiteboxTool for LidarTINGridding {
 
                         let _ = output.write().expect("Error writing file.");
 
-                        tx2.send(tile).unwrap();
+                        if tx2.send(WorkerMessage::Data(tile)).is_err() {
+                            return;
+                        }
                     }
                 }
             });
         }
+        drop(tx2);
 
         let mut progress: i32;
         let mut old_progress: i32 = -1;
         for tile in 0..inputs.len() {
-            let tile_completed = rx2.recv().unwrap();
+            let tile_completed = match recv_data(&rx2, reporter) {
+                Ok(tile_completed) => tile_completed,
+                Err(_) => {
+                    // the worker threads have stopped
+                    reporter.check_cancelled()?;
+                    return Err(Error::new(
+                        ErrorKind::Other,
+                        "The interpolation ended before all of the tiles were processed.",
+                    ));
+                }
+            };
+            reporter.check_cancelled()?;
             if verbose {
                 if tile <= 98 {
                     println!(
@@ -1182,7 +1143,7 @@ impl WhiteboxTool for LidarTINGridding {
                 }
                 progress = (100.0_f64 * tile as f64 / (inputs.len() - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
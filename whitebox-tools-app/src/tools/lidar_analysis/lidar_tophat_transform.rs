@@ -9,14 +9,13 @@ License: MIT
 use whitebox_lidar::*;
 use whitebox_common::structures::{DistanceMetric, FixedRadiusSearch2D};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a white [top-hat transform](https://en.wikipedia.org/wiki/Top-hat_transform) on a LiDAR point cloud (`--input`).
 /// A top-hat transform is a common digital image processing operation used for various tasks, such
@@ -150,54 +149,14 @@ impl WhiteboxTool for LidarTophatTransform {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut search_radius = -1f64;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let search_radius = parsed.get_f64("radius").unwrap_or(1f64);
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-radius" {
-                search_radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -210,13 +169,8 @@ impl WhiteboxTool for LidarTophatTransform {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -246,10 +200,11 @@ impl WhiteboxTool for LidarTophatTransform {
                 // low points really mess with the tophat-transform so exclude classified noise
                 frs.insert(p.x, p.y, i);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Binning points: {}%", progress);
+                    reporter.progress("Binning points", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -263,13 +218,13 @@ impl WhiteboxTool for LidarTophatTransform {
         /////////////
         let frs = Arc::new(frs); // wrap FRS in an Arc
         let input = Arc::new(input); // wrap input in an Arc
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let frs = frs.clone();
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut index_n: usize;
                 let mut z_n: f64;
                 let mut min_z: f64;
@@ -290,7 +245,9 @@ impl WhiteboxTool for LidarTophatTransform {
                     } else {
                         min_z = 0f64;
                     }
-                    tx.send((i, min_z)).unwrap();
+                    if tx.send((i, min_z)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -298,10 +255,11 @@ impl WhiteboxTool for LidarTophatTransform {
         for i in 0..n_points {
             let data = rx.recv().expect("Error receiving data from thread.");
             neighbourhood_min[data.0] = data.1;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Erosion: {}%", progress);
+                    reporter.progress("Erosion", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -316,7 +274,7 @@ impl WhiteboxTool for LidarTophatTransform {
             let input = input.clone();
             let neighbourhood_min = neighbourhood_min.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut index_n: usize;
                 let mut z_n: f64;
                 let mut max_z: f64;
@@ -337,7 +295,9 @@ impl WhiteboxTool for LidarTophatTransform {
                     } else {
                         max_z = 0f64;
                     }
-                    tx.send((i, max_z)).unwrap();
+                    if tx.send((i, max_z)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -347,10 +307,11 @@ impl WhiteboxTool for LidarTophatTransform {
             // let z = input.get_point_info(data.0).z;
             let z = input.get_transformed_coords(data.0).z;
             residuals[data.0] = z - data.1;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Dilation: {}%", progress);
+                    reporter.progress("Dilation", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -490,10 +451,11 @@ impl WhiteboxTool for LidarTophatTransform {
                 }
             }
             output.add_point_record(pr2);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Saving data: {}%", progress);
+                    reporter.progress("Saving data", progress as usize);
                     old_progress = progress;
                 }
             }
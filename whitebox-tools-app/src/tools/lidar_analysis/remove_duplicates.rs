@@ -9,14 +9,13 @@ License: MIT
 use whitebox_lidar::*;
 use whitebox_common::structures::{DistanceMetric, FixedRadiusSearch2D};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool removes duplicate points from a LiDAR data set. Duplicates are determined
 /// by their x, y, and optionally (`--include_z`) z coordinates.
@@ -135,47 +134,15 @@ impl WhiteboxTool for LidarRemoveDuplicates {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file: String = "".to_string();
-        let mut output_file: String = "".to_string();
-        let mut include_z = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file: String = parsed.get_string("input").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let include_z = parsed.get_bool("include_z");
         let search_radius: f64 = 0.25; // arbitrarily small distance.
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-include_z" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    include_z = true;
-                }
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -188,13 +155,8 @@ impl WhiteboxTool for LidarRemoveDuplicates {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep = path::MAIN_SEPARATOR;
-        if !input_file.contains(sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("reading input LiDAR file...");
@@ -221,10 +183,11 @@ impl WhiteboxTool for LidarRemoveDuplicates {
             // let p: PointData = input.get_point_info(i);
             let p = input.get_transformed_coords(i);
             frs.insert(p.x, p.y, i);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Binning points: {}%", progress);
+                    reporter.progress("Binning points", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -232,13 +195,13 @@ impl WhiteboxTool for LidarRemoveDuplicates {
 
         let frs = Arc::new(frs); // wrap FRS in an Arc
         let input = Arc::new(input); // wrap input in an Arc
-        let num_procs = num_cpus::get();
+        let num_procs = whitebox_common::utils::ThreadPool::from_settings().num_threads();
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let frs = frs.clone();
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut index_n: usize;
                 let mut dup: bool;
                 for point_num in (0..n_points).filter(|point_num| point_num % num_procs == tid) {
@@ -264,7 +227,9 @@ impl WhiteboxTool for LidarRemoveDuplicates {
                             }
                         }
                     }
-                    tx.send((point_num, dup)).unwrap();
+                    if tx.send((point_num, dup)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -273,10 +238,11 @@ impl WhiteboxTool for LidarRemoveDuplicates {
         for i in 0..n_points {
             let data = rx.recv().expect("Error receiving data from thread.");
             is_duplicate[data.0] = data.1;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Erosion: {}%", progress);
+                    reporter.progress("Erosion", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -290,10 +256,11 @@ impl WhiteboxTool for LidarRemoveDuplicates {
             if !is_duplicate[i] {
                 output.add_point_record(input.get_record(i));
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / num_points) as i32;
                 if progress != old_progress {
-                    println!("Saving data: {}%", progress);
+                    reporter.progress("Saving data", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -11,7 +11,6 @@ use whitebox_lidar::*;
 use whitebox_common::structures::{BoundingBox, Point2D};
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs;
@@ -19,7 +18,7 @@ use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool copies LiDAR tiles overlapping with a polygon into an output directory. In actuality, the tool performs
 /// point-in-polygon operations, using the four corner points, the center point, and the four mid-edge points of each
@@ -68,7 +67,7 @@ impl SelectTilesByPolygon {
 
         parameters.push(ToolParameter {
             name: "Input Vector Polygon File".to_owned(),
-            flags: vec!["--polygons".to_owned()],
+            flags: vec!["--polygon".to_owned(), "--polygons".to_owned()],
             description: "Input vector polygons file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
                 VectorGeometryType::Polygon,
@@ -142,58 +141,21 @@ impl WhiteboxTool for SelectTilesByPolygon {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_directory: String = "".to_string();
-        let mut output_directory: String = "".to_string();
-        let mut polygons_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_directory: String = parsed.get_string("indir").unwrap_or_default();
+        let output_directory: String = parsed.get_string("outdir").unwrap_or_default();
+        let mut polygons_file = parsed.get_string("polygons").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-indir" {
-                input_directory = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-outdir" {
-                output_directory = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-polygon" || flag_val == "-polygons" {
-                polygons_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
         let start = Instant::now();
-        let sep: String = path::MAIN_SEPARATOR.to_string();
 
         if input_directory.is_empty() {
             input_directory = working_directory.clone().to_string();
         }
-        if !polygons_file.contains(&sep) && !polygons_file.contains("/") {
-            polygons_file = format!("{}{}", working_directory, polygons_file);
-        }
+        polygons_file = resolve_input_path(&polygons_file, working_directory)?;
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -244,6 +206,7 @@ impl WhiteboxTool for SelectTilesByPolygon {
         // place the bounding boxes of each of the polygons into a vector
         let mut bb: Vec<BoundingBox> = Vec::with_capacity(num_records);
         for record_num in 0..polygons.num_records {
+            reporter.check_cancelled()?;
             let record = polygons.get_record(record_num);
             bb.push(BoundingBox::new(
                 record.x_min,
@@ -256,12 +219,8 @@ impl WhiteboxTool for SelectTilesByPolygon {
         let report_copy = Arc::new(Mutex::new(true));
         let inputs = Arc::new(inputs);
         let bb = Arc::new(bb);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         let num_tiles = inputs.len();
         let tile_list = Arc::new(Mutex::new(0..num_tiles));
@@ -275,7 +234,7 @@ impl WhiteboxTool for SelectTilesByPolygon {
             // copy over the string parameters
             let input_directory = input_directory.clone();
             let output_directory = output_directory.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut point_in_poly: bool;
                 let mut start_point_in_part: usize;
                 let mut end_point_in_part: usize;
@@ -445,7 +404,9 @@ impl WhiteboxTool for SelectTilesByPolygon {
                         }
                     }
 
-                    tx.send(point_in_poly).unwrap();
+                    if tx.send(point_in_poly).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -465,10 +426,11 @@ impl WhiteboxTool for SelectTilesByPolygon {
                     *report_copy = false;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tile as f64 / (num_tiles - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -12,7 +12,8 @@ use std;
 use std::io::{Error, ErrorKind};
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::{env, fs, path, thread};
+use std::{env, fs, path};
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to convert one or more *zLidar* files ('*.zlidar') files into the *LAS*
 /// LiDAR data format. [zLidar](https://jblindsay.github.io/zLidar_spec/intro.html) files are a compressed
@@ -42,7 +43,7 @@ impl ZlidarToLas {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input ZLidar Files".to_owned(),
-            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--inputs".to_owned()],
             description: "Input ZLidar files.".to_owned(),
             parameter_type: ParameterType::FileList(ParameterFileType::Lidar),
             default_value: None,
@@ -128,41 +129,13 @@ impl WhiteboxTool for ZlidarToLas {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files: String = String::new();
-        let mut output_directory: String = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files: String = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_directory: String = parsed.get_string("outdir").unwrap_or_default();
 
         // read the arguments
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" || flag_val == "-input" {
-                if keyval {
-                    input_files = vec[1].to_string();
-                } else {
-                    input_files = args[i + 1].to_string();
-                }
-            } else if flag_val == "-outdir" {
-                output_directory = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -245,12 +218,8 @@ impl WhiteboxTool for ZlidarToLas {
         let working_directory = Arc::new(working_directory.to_owned());
         let output_directory = Arc::new(output_directory.clone());
         let tile_list = Arc::new(Mutex::new(0..num_files));
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for _ in 0..num_procs {
             let inputs = inputs.clone();
@@ -258,7 +227,7 @@ impl WhiteboxTool for ZlidarToLas {
             let working_directory = working_directory.clone();
             let output_directory = output_directory.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut k = 0;
                 let mut progress: usize;
                 let mut old_progress: usize = 1;
@@ -271,9 +240,7 @@ impl WhiteboxTool for ZlidarToLas {
 
                     let mut input_file = inputs[k].replace("\"", "").clone();
                     if !input_file.is_empty() {
-                        if !input_file.contains(sep) && !input_file.contains("/") {
-                            input_file = format!("{}{}", working_directory, input_file);
-                        }
+                        input_file = resolve_path(&input_file, &working_directory);
 
                         let input: LasFile = match LasFile::new(&input_file, "r") {
                             Ok(lf) => lf,
@@ -304,7 +271,9 @@ impl WhiteboxTool for ZlidarToLas {
                                 progress =
                                     (100.0_f64 * (p + 1) as f64 / (n_points - 1) as f64) as usize;
                                 if progress != old_progress {
-                                    println!("Creating output: {}%", progress);
+                                    if tx.send(WorkerMessage::Progress("Creating output", progress)).is_err() {
+                                        return;
+                                    }
                                     old_progress = progress;
                                 }
                             }
@@ -315,9 +284,13 @@ impl WhiteboxTool for ZlidarToLas {
                             }
                             Err(e) => println!("error while writing: {:?}", e),
                         };
-                        tx.send(short_filename.clone()).unwrap();
+                        if tx.send(WorkerMessage::Data(short_filename.clone())).is_err() {
+                            return;
+                        }
                     } else {
-                        tx.send(format!("Empty file name for tile {}.", k)).unwrap();
+                        if tx.send(WorkerMessage::Data(format!("Empty file name for tile {}.", k))).is_err() {
+                            return;
+                        }
                     }
                 }
             });
@@ -326,7 +299,7 @@ impl WhiteboxTool for ZlidarToLas {
         let mut progress: usize;
         let mut old_progress: usize = 1;
         for tile in 0..num_files {
-            let file_nm = rx.recv().expect("Error receiving data from thread.");
+            let file_nm = recv_data(&rx, reporter).expect("Error receiving data from thread.");
             if verbose && !file_nm.contains("Empty") && num_files > 1 && tile < 99 {
                 println!("Completed conversion of {}", file_nm);
             } else if verbose && tile == 99 {
@@ -335,10 +308,11 @@ impl WhiteboxTool for ZlidarToLas {
             } else if file_nm.to_lowercase().contains("empty file name") {
                 println!("{}", file_nm);
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tile as f64 / (num_files - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
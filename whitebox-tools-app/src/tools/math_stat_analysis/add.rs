@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool creates a new raster in which each grid cell is equal to the addition of the corresponding grid
 /// cells in two input rasters or one input raster and a constant value. If two images are input, both
@@ -45,7 +44,7 @@ impl Add {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input File Or Constant Value".to_owned(),
-            flags: vec!["--input1".to_owned()],
+            flags: vec!["--i1".to_owned(), "--input1".to_owned()],
             description: "Input raster file or constant value.".to_owned(),
             parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
             default_value: None,
@@ -54,7 +53,7 @@ impl Add {
 
         parameters.push(ToolParameter {
             name: "Input File Or Constant Value".to_owned(),
-            flags: vec!["--input2".to_owned()],
+            flags: vec!["--i2".to_owned(), "--input2".to_owned()],
             description: "Input raster file or constant value.".to_owned(),
             parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
             default_value: None,
@@ -135,46 +134,12 @@ impl WhiteboxTool for Add {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input1 = String::new();
-        let mut input2 = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i1" || vec[0].to_lowercase() == "--input1" {
-                if keyval {
-                    input1 = vec[1].to_string();
-                } else {
-                    input1 = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-i2" || vec[0].to_lowercase() == "--input2" {
-                if keyval {
-                    input2 = vec[1].to_string();
-                } else {
-                    input2 = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -187,14 +152,10 @@ impl WhiteboxTool for Add {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         // Are either of the inputs constants?
         let mut input1_constant = f64::NEG_INFINITY;
@@ -206,9 +167,7 @@ impl WhiteboxTool for Add {
             Err(_) => false,
         };
         if !input1_is_constant {
-            if !input1.contains(&sep) && !input1.contains("/") {
-                input1 = format!("{}{}", working_directory, input1);
-            }
+            input1 = resolve_input_path(&input1, working_directory)?;
         }
 
         let mut input2_constant = f64::NEG_INFINITY;
@@ -220,9 +179,7 @@ impl WhiteboxTool for Add {
             Err(_) => false,
         };
         if !input2_is_constant {
-            if !input2.contains(&sep) && !input2.contains("/") {
-                input2 = format!("{}{}", working_directory, input2);
-            }
+            input2 = resolve_input_path(&input2, working_directory)?;
         }
 
         if input1_is_constant && input2_is_constant {
@@ -241,29 +198,27 @@ impl WhiteboxTool for Add {
             let columns = in2.configs.columns as isize;
             let nodata2 = in2.configs.nodata;
 
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let in2 = in2.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z2: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata2; columns as usize];
                         for col in 0..columns {
                             z2 = in2[(row, col)];
-                            if z2 != nodata2 {
+                            if !is_nodata(z2, nodata2) {
                                 data[col as usize] = input1_constant + z2;
                             } else {
                                 data[col as usize] = nodata2;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -280,10 +235,11 @@ impl WhiteboxTool for Add {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(row, data);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -324,29 +280,27 @@ impl WhiteboxTool for Add {
             let columns = in1.configs.columns as isize;
             let nodata1 = in1.configs.nodata;
 
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let in1 = in1.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z1: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut data: Vec<f64> = vec![nodata1; columns as usize];
                         for col in 0..columns {
                             z1 = in1[(row, col)];
-                            if z1 != nodata1 {
+                            if !is_nodata(z1, nodata1) {
                                 data[col as usize] = z1 + input2_constant;
                             } else {
                                 data[col as usize] = nodata1;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -363,10 +317,11 @@ impl WhiteboxTool for Add {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(row, data);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -412,22 +367,18 @@ impl WhiteboxTool for Add {
 
             // make sure the input files have the same size
             if in1.configs.rows != in2.configs.rows || in1.configs.columns != in2.configs.columns {
-                return Err(Error::new(ErrorKind::InvalidInput,
-                                    "The input files must have the same number of rows and columns and spatial extent."));
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
             }
+            warn_if_crs_differs(&in1, &in2, verbose);
 
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let in1 = in1.clone();
                 let in2 = in2.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z1: f64;
                     let mut z2: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -435,13 +386,15 @@ impl WhiteboxTool for Add {
                         for col in 0..columns {
                             z1 = in1[(row, col)];
                             z2 = in2[(row, col)];
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                                 data[col as usize] = z1 + z2;
                             } else {
                                 data[col as usize] = nodata1;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -451,10 +404,11 @@ impl WhiteboxTool for Add {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(row, data);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -8,20 +8,19 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::path::Path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs an [Analysis of variance](https://en.wikipedia.org/wiki/Analysis_of_variance) (ANOVA)
 /// test on the distribution of values in a raster (`--input`) among a group of features (`--features`). The
@@ -135,47 +134,12 @@ impl WhiteboxTool for Anova {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut feature_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-features" || vec[0].to_lowercase() == "--features"
-            {
-                if keyval {
-                    feature_file = vec[1].to_string();
-                } else {
-                    feature_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut feature_file = parsed.get_string("features").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -188,20 +152,12 @@ impl WhiteboxTool for Anova {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !feature_file.contains(&sep) && !feature_file.contains("/") {
-            feature_file = format!("{}{}", working_directory, feature_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        feature_file = resolve_input_path(&feature_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
         if !output_file.ends_with(".html") {
             output_file = output_file + ".html";
         }
@@ -301,7 +257,9 @@ impl WhiteboxTool for Anova {
         let features = Arc::new(Raster::new(&feature_file, "r")?);
         let nodata_features = features.configs.nodata;
         if features.configs.columns as isize != columns || features.configs.rows as isize != rows {
-            panic!("The input and feature definition rasters must have the same number of rows and columns.");
+            return Err(ErrorCause::GridMismatch.error(
+                "The input and feature definition rasters must have the same number of rows and columns.",
+            ));
         }
         // How many features/classes are there?
         // let mut z: f64;
@@ -309,18 +267,14 @@ impl WhiteboxTool for Anova {
         // let mut id_int: i32;
         let mut vec_id: usize;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let features = features.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut id: f64;
                 let mut id_int: i32;
@@ -333,7 +287,7 @@ impl WhiteboxTool for Anova {
                     for col in 0..columns {
                         z = input.get_value(row, col);
                         id = features.get_value(row, col);
-                        if z != nodata && id != nodata_features {
+                        if !is_nodata(z, nodata) && !is_nodata(id, nodata_features) {
                             id_int = id.round() as i32;
                             if id_int > max_id {
                                 max_id = id_int;
@@ -346,8 +300,9 @@ impl WhiteboxTool for Anova {
                             overall_sum_sqr += z * z;
                         }
                     }
-                    tx.send((overall_n, overall_sum, overall_sum_sqr, min_id, max_id))
-                        .unwrap();
+                    if tx.send((overall_n, overall_sum, overall_sum_sqr, min_id, max_id)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -369,10 +324,11 @@ impl WhiteboxTool for Anova {
                 max_id = e;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (loop 1 of 2): {}%", progress);
+                    reporter.progress("Progress (loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -396,7 +352,7 @@ impl WhiteboxTool for Anova {
             for col in 0..columns as isize {
                 z = input.get_value(row, col);
                 id = features.get_value(row, col);
-                if z != nodata && id != nodata_features {
+                if !is_nodata(z, nodata) && !is_nodata(id, nodata_features) {
                     // class statistics
                     id_int = id.round() as i32;
                     vec_id = (id_int - min_id) as usize;
@@ -405,10 +361,11 @@ impl WhiteboxTool for Anova {
                     sum_sqr[vec_id] += z * z;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
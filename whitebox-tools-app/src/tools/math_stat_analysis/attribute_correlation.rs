@@ -147,40 +147,11 @@ impl WhiteboxTool for AttributeCorrelation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -193,16 +164,12 @@ impl WhiteboxTool for AttributeCorrelation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
         let start = Instant::now();
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
         if output_file.len() == 0 {
             // output_file not specified and should be based on input file
             let p = path::Path::new(&input_file);
@@ -211,9 +178,7 @@ impl WhiteboxTool for AttributeCorrelation {
             extension.push_str(ext);
             output_file = input_file.replace(&extension, ".html");
         }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading vector data...")
@@ -270,11 +235,12 @@ impl WhiteboxTool for AttributeCorrelation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (vector_data.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -334,15 +300,11 @@ impl WhiteboxTool for AttributeCorrelation {
             }
             i += 1;
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * a as f64 / (num_fields - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!(
-                        "Calculating the correlation matrix ({} of {}): {}%",
-                        (a + 1),
-                        num_fields,
-                        progress
-                    );
+                    reporter.progress(&format!("Calculating the correlation matrix ({} of {})", (a + 1), num_fields), progress);
                     old_progress = progress;
                 }
             }
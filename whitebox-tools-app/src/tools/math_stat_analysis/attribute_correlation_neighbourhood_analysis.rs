@@ -185,88 +185,29 @@ impl WhiteboxTool for AttributeCorrelationNeighbourhoodAnalysis {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut field_name1 = String::new();
-        let mut field_name2 = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let field_name1 = parsed.get_string("field1").unwrap_or_default();
+        let field_name2 = parsed.get_string("field2").unwrap_or_default();
         let mut radius = 0f64;
-        let mut min_points = 0usize;
+        let min_points = parsed.get_usize("min_points")?.unwrap_or(0usize);
         let mut stat_type = String::from("pearson");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_f64("radius") {
+            radius = value;
+            radius *= radius; // the K-D tree structure actually needs the squared-radius because squared distances are used.
         }
-
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field1" {
-                field_name1 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field2" {
-                field_name2 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-radius" {
-                radius = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                radius *= radius; // the K-D tree structure actually needs the squared-radius because squared distances are used.
-            } else if flag_val == "-min_points" {
-                min_points = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-stat" {
-                let val = if keyval {
-                    vec[1].to_lowercase()
-                } else {
-                    args[i + 1].to_lowercase()
-                };
-                stat_type = if val.contains("son") {
-                    "pearson".to_string()
-                } else if val.contains("kendall") {
-                    "kendall".to_string()
-                } else {
-                    "spearman".to_string()
-                };
-            }
+        if let Some(value) = parsed.get_string("stat") {
+            let val = value.to_lowercase();
+            stat_type = if val.contains("son") {
+                "pearson".to_string()
+            } else if val.contains("kendall") {
+                "kendall".to_string()
+            } else {
+                "spearman".to_string()
+            };
         }
 
         if verbose {
@@ -280,14 +221,10 @@ impl WhiteboxTool for AttributeCorrelationNeighbourhoodAnalysis {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
         if verbose {
             println!("Reading data...")
@@ -349,10 +286,11 @@ impl WhiteboxTool for AttributeCorrelationNeighbourhoodAnalysis {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                 if progress != old_progress {
-                    println!("Reading points: {}%", progress);
+                    reporter.progress("Reading points", progress);
                     old_progress = progress;
                 }
             }
@@ -687,10 +625,11 @@ impl WhiteboxTool for AttributeCorrelationNeighbourhoodAnalysis {
                         .set_value(record_num, "PVALUE", FieldData::Real(pvalue));
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (record_num + 1) as f64 / points.len() as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
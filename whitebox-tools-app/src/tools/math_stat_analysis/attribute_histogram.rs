@@ -2,19 +2,15 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 12/04/2018
-Last Modified: 12/10/2018
+Last Modified: 16/10/2026
 License: MIT
 */
 
-use whitebox_common::rendering::html::*;
-use whitebox_common::rendering::Histogram;
+use whitebox_common::rendering::{Histogram, Report};
 use crate::tools::*;
 use whitebox_vector::{FieldData, Shapefile};
 use std::env;
 use std::f64;
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::BufWriter;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::process::Command;
@@ -25,7 +21,8 @@ use std::process::Command;
 /// of the fields (`--field`) contained in the associated attribute table. The tool output
 /// (`--output`) is an HTML formatted histogram analysis report. If the specified field
 /// is non-numerical, the tool will produce a bar-chart of class frequency, similar to the
-/// tabular output of the `ListUniqueValues` tool.
+/// tabular output of the `ListUniqueValues` tool. When the `report_json` setting is enabled (e.g.
+/// `--report_json`), the bin frequencies are also written to a JSON file of the same name.
 ///
 /// # See Also
 /// `ListUniqueValues`, `RasterHistogram`
@@ -149,47 +146,12 @@ impl WhiteboxTool for AttributeHistogram {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut field_name = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-field" {
-                field_name = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let field_name = parsed.get_string("field").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -202,19 +164,13 @@ impl WhiteboxTool for AttributeHistogram {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
         let start = Instant::now();
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading vector data...")
@@ -267,11 +223,12 @@ impl WhiteboxTool for AttributeHistogram {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (vector_data.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding min and max: {}%", progress);
+                    reporter.progress("Finding min and max", progress);
                     old_progress = progress;
                 }
             }
@@ -304,11 +261,12 @@ impl WhiteboxTool for AttributeHistogram {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (vector_data.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Binning data: {}%", progress);
+                    reporter.progress("Binning data", progress);
                     old_progress = progress;
                 }
             }
@@ -323,32 +281,10 @@ impl WhiteboxTool for AttributeHistogram {
             );
         }
 
-        let f = File::create(output_file.clone())?;
-        let mut writer = BufWriter::new(f);
-
-        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
-        <head>
-            <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
-            <title>Histogram Analysis</title>"#.as_bytes())?;
-
-        // get the style sheet
-        writer.write_all(&get_css().as_bytes())?;
-
-        writer.write_all(
-            &r#"</head>
-        <body>
-            <h1>Histogram Analysis</h1>"#
-                .as_bytes(),
-        )?;
-
-        writer.write_all(
-            &format!("<p><strong>Input</strong>: {}</p>", input_file.clone()).as_bytes(),
-        )?;
-        writer.write_all(
-            &format!("<p><strong>Field Name</strong>: {}</p>", field_name.clone()).as_bytes(),
-        )?;
-
-        let histo = Histogram {
+        let mut report = Report::new("Histogram Analysis");
+        report.add_property("Input", input_file.clone());
+        report.add_property("Field Name", field_name.clone());
+        report.add_histogram(Histogram {
             parent_id: "histo".to_owned(),
             width: 700f64,
             height: 500f64,
@@ -357,15 +293,8 @@ impl WhiteboxTool for AttributeHistogram {
             bin_width: bin_width,
             x_axis_label: field_name.to_owned(),
             cumulative: false,
-        };
-
-        writer.write_all(
-            &format!("<div id='histo' align=\"center\">{}</div>", histo.get_svg()).as_bytes(),
-        )?;
-
-        writer.write_all("</body>".as_bytes())?;
-
-        let _ = writer.flush();
+        });
+        report.write(&output_file)?;
 
         // println!("freq. data: {:?}", freq_data);
 
@@ -8,18 +8,17 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// The Crispness Index (*C*) provides a means of quantifying the crispness, or fuzziness, of a membership
 /// probability (MP) image. MP images describe the probability of each grid cell belonging to some feature
@@ -101,7 +100,7 @@ impl CrispnessIndex {
         }
         let usage = format!(
             ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.tif
->>.*{0} -r={1} -v --wd=\"*path*to*data*\" -o=crispness.html",
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=input.tif -o=crispness.html",
             short_exe, name
         )
         .replace("*", &sep);
@@ -156,39 +155,11 @@ impl WhiteboxTool for CrispnessIndex {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -201,14 +172,10 @@ impl WhiteboxTool for CrispnessIndex {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
         if output_file.len() == 0 {
             // output_file not specified and should be based on input file
             let p = path::Path::new(&input_file);
@@ -217,9 +184,7 @@ impl WhiteboxTool for CrispnessIndex {
             extension.push_str(ext);
             output_file = input_file.replace(&extension, ".html");
         }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -236,17 +201,13 @@ impl WhiteboxTool for CrispnessIndex {
         //let (mean, stdev) = input.calculate_mean_and_stdev();
 
         // calculate the number of downslope cells
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut n = 0;
@@ -254,7 +215,7 @@ impl WhiteboxTool for CrispnessIndex {
                     let mut warning = false;
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             if z < 0f64 || z > 1f64 {
                                 warning = true;
                             }
@@ -262,7 +223,9 @@ impl WhiteboxTool for CrispnessIndex {
                             s += z;
                         }
                     }
-                    tx.send((n, s, warning)).unwrap();
+                    if tx.send((n, s, warning)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -278,10 +241,11 @@ impl WhiteboxTool for CrispnessIndex {
                 warning = true;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -293,17 +257,19 @@ impl WhiteboxTool for CrispnessIndex {
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut total_dev = 0f64;
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             total_dev += (z - mean) * (z - mean);
                         }
                     }
-                    tx.send(total_dev).unwrap();
+                    if tx.send(total_dev).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -312,10 +278,11 @@ impl WhiteboxTool for CrispnessIndex {
         for row in 0..rows {
             total_dev += rx.recv().expect("Error receiving data from thread.");
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -13,7 +13,7 @@ use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::process::Command;
 
@@ -141,47 +141,12 @@ impl WhiteboxTool for CrossTabulation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1: String = String::new();
-        let mut input_file2: String = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i1" || flag_val == "-input1" {
-                if keyval {
-                    input_file1 = vec[1].to_string();
-                } else {
-                    input_file1 = args[i + 1].to_string();
-                }
-            } else if flag_val == "-i2" || flag_val == "-input2" {
-                if keyval {
-                    input_file2 = vec[1].to_string();
-                } else {
-                    input_file2 = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1: String = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2: String = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -194,22 +159,14 @@ impl WhiteboxTool for CrossTabulation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
         let start = Instant::now();
 
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let input1 = Raster::new(&input_file1, "r")?;
         let rows = input1.configs.rows as isize;
@@ -240,7 +197,7 @@ impl WhiteboxTool for CrossTabulation {
             for col in 0..columns {
                 z1 = input1.get_value(row, col);
                 z2 = input2.get_value(row, col);
-                if z1 != nodata1 && z2 != nodata2 {
+                if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                     index1 = (z1.round() as isize - min1) as usize;
                     index2 = (z2.round() as isize - min2) as usize;
                     class_exists1[index1] = true;
@@ -248,10 +205,11 @@ impl WhiteboxTool for CrossTabulation {
                     contingency_table[index1][index2] += 1;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Creating contingency table: {}%", progress);
+                    reporter.progress("Creating contingency table", progress);
                     old_progress = progress;
                 }
             }
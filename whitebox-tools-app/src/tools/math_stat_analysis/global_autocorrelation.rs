@@ -0,0 +1,722 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{
+    expected_join_counts, gearys_c, global_morans_i, join_counts, permutation_p_value,
+};
+use whitebox_common::structures::Point2D;
+use crate::tools::*;
+use whitebox_vector::*;
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use rand::prelude::*;
+use rand::rngs::SmallRng;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool measures the global spatial autocorrelation of a numeric attribute of a vector layer,
+/// i.e. the overall tendency for features with similar values to be located near one another. Three
+/// statistics are calculated:
+///
+/// - **Moran's *I***, which ranges from approximately -1 (dispersed) to 1 (clustered), with an expected
+///   value of -1/(n-1) in the absence of spatial autocorrelation.
+/// - **Geary's *C***, which is more sensitive to differences between neighbouring values. Values less
+///   than one indicate positive spatial autocorrelation and values greater than one indicate negative
+///   spatial autocorrelation.
+/// - **Join counts** (black-black, white-white, and black-white), which describe the autocorrelation of
+///   a binary variable. Features are classed as 'black' if their value is greater than or equal to the
+///   threshold (`--threshold`), and 'white' otherwise. By default, the threshold is the mean value, such
+///   that binary (0/1) attributes are handled appropriately.
+///
+/// The statistical significance of each statistic is evaluated using a Monte Carlo permutation test, in
+/// which the attribute values are randomly reassigned to the features a number of times (`--permutations`)
+/// and the statistics are recalculated for each permutation. The pseudo p-value is the proportion of the
+/// permutations (plus the observed arrangement) with a statistic at least as extreme as the observed
+/// value, in the direction of the observed deviation. Unlike the analytical tests used by the
+/// `ImageAutocorrelation` tool, the permutation test does not rely on the assumption of normality.
+///
+/// The neighbours of each feature are determined by the `--weights` parameter, using binary weights.
+/// With `contiguity` weights, polygons and lines are neighbours if they share at least one vertex. With
+/// `distance` weights, the neighbours of a feature are all features within the distance band
+/// (`--distance`), and with `knn` weights, the neighbours are the *k* nearest features (`--k`); the k-nearest
+/// neighbour relation is made symmetric. Features with null attribute values are excluded.
+///
+/// The output report (`--output`) is an HTML file, unless the file name has a *.json* extension, in which case
+/// the results are written in JSON format, which is convenient for further processing.
+///
+/// # See Also
+/// `ImageGlobalAutocorrelation`, `ImageAutocorrelation`, `HotSpotAnalysis`
+pub struct GlobalAutocorrelation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl GlobalAutocorrelation {
+    pub fn new() -> GlobalAutocorrelation {
+        // public constructor
+        let name = "GlobalAutocorrelation".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description = "Calculates Moran's I, Geary's C, and join count statistics for a vector attribute, with permutation inference.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Numeric field to analyze.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Report File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output HTML or JSON report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Spatial Weights".to_owned(),
+            flags: vec!["--weights".to_owned()],
+            description: "Method used to define neighbours; options are 'contiguity', 'distance' and 'knn'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "contiguity".to_owned(),
+                "distance".to_owned(),
+                "knn".to_owned(),
+            ]),
+            default_value: Some("distance".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Distance Band".to_owned(),
+            flags: vec!["--distance".to_owned()],
+            description: "Distance band used with distance weights.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Neighbours (k)".to_owned(),
+            flags: vec!["--k".to_owned()],
+            description: "Number of nearest neighbours used with knn weights.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("8".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Permutations".to_owned(),
+            flags: vec!["--permutations".to_owned()],
+            description: "Number of random permutations used to evaluate significance.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("999".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Join Count Threshold".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Values greater than or equal to the threshold are 'black' in the join count analysis; the default is the mean.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=counties.shp --field=RATE -o=report.html --weights=contiguity --permutations=9999",
+            short_exe, name
+        ).replace("*", &sep);
+
+        GlobalAutocorrelation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for GlobalAutocorrelation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let field_name = parsed.get_string("field").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut weights = String::from("distance");
+        let distance = parsed.get_f64("distance").unwrap_or(0f64);
+        let k = parsed.get_usize("k")?.unwrap_or(8usize);
+        let num_permutations = parsed.get_usize("permutations")?.unwrap_or(999usize);
+        let mut threshold = parsed.get_f64("threshold").unwrap_or(f64::NAN);
+
+        // read the arguments
+        if let Some(value) = parsed.get_string("weights") {
+            weights = value.to_lowercase();
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        let weights_type = if weights.contains("contig") {
+            WeightsType::Contiguity
+        } else if weights.contains("knn") || weights.contains("nearest") {
+            WeightsType::NearestNeighbours
+        } else {
+            WeightsType::DistanceBand
+        };
+        if weights_type == WeightsType::DistanceBand && distance <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A positive distance band (--distance) must be specified when using distance weights.",
+            ));
+        }
+        if weights_type == WeightsType::NearestNeighbours && k == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of neighbours (--k) must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+        let base_type = input.header.shape_type.base_shape_type();
+        if weights_type == WeightsType::Contiguity
+            && (base_type == ShapeType::Point || base_type == ShapeType::MultiPoint)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Contiguity weights cannot be used with point data; use distance or knn weights instead.",
+            ));
+        }
+        match input.attributes.get_field_num(&field_name) {
+            Some(i) if input.attributes.is_field_numeric(i) => {}
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The specified field must be a numeric field of the input attribute table.",
+                ))
+            }
+        }
+
+        // read the observations; features with null values are excluded
+        let mut record_nums = vec![];
+        let mut values = vec![];
+        let mut locations = vec![];
+        for record_num in 0..input.num_records {
+            reporter.check_cancelled()?;
+            let record = input.get_record(record_num);
+            if record.shape_type == ShapeType::Null || record.num_points == 0 {
+                continue;
+            }
+            let value = match input.attributes.get_value(record_num, &field_name) {
+                FieldData::Int(v) => v as f64,
+                FieldData::Real(v) => v,
+                _ => f64::NAN,
+            };
+            if !value.is_finite() {
+                continue;
+            }
+            record_nums.push(record_num);
+            values.push(value);
+            locations.push(feature_location(record, base_type));
+        }
+        let n = values.len();
+        if n < 4 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input file must contain at least four features with non-null values.",
+            ));
+        }
+
+        if verbose {
+            println!("Finding neighbours...")
+        };
+        let mut neighbours: Vec<Vec<(usize, f64)>> = vec![vec![]; n];
+        match weights_type {
+            WeightsType::Contiguity => {
+                let mut vertex_map: HashMap<(u64, u64), Vec<usize>> = HashMap::new();
+                for i in 0..n {
+                    let record = input.get_record(record_nums[i]);
+                    for p in &record.points {
+                        let entry = vertex_map.entry((p.x.to_bits(), p.y.to_bits())).or_insert(vec![]);
+                        if entry.last() != Some(&i) {
+                            entry.push(i);
+                        }
+                    }
+                }
+                for i in 0..n {
+                    let record = input.get_record(record_nums[i]);
+                    let mut set = HashSet::new();
+                    for p in &record.points {
+                        for &j in &vertex_map[&(p.x.to_bits(), p.y.to_bits())] {
+                            if j != i && set.insert(j) {
+                                neighbours[i].push((j, 1f64));
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {
+                let mut tree = KdTree::with_capacity(2, 64);
+                for i in 0..n {
+                    tree.add([locations[i].x, locations[i].y], i).unwrap();
+                }
+                let mut pairs = HashSet::new();
+                for i in 0..n {
+                    let p = locations[i];
+                    let ret = if weights_type == WeightsType::DistanceBand {
+                        tree.within(&[p.x, p.y], distance * distance, &squared_euclidean)
+                            .unwrap()
+                    } else {
+                        tree.nearest(&[p.x, p.y], (k + 1).min(n), &squared_euclidean)
+                            .unwrap()
+                    };
+                    let mut num_found = 0;
+                    for (_, &j) in ret.iter().filter(|(_, &j)| j != i) {
+                        if weights_type == WeightsType::NearestNeighbours && num_found == k {
+                            break;
+                        }
+                        num_found += 1;
+                        // the weights must be symmetric
+                        pairs.insert((i.min(j), i.max(j)));
+                    }
+                }
+                for (i, j) in pairs {
+                    neighbours[i].push((j, 1f64));
+                    neighbours[j].push((i, 1f64));
+                }
+            }
+        }
+        let s0: f64 = neighbours.iter().map(|nb| nb.len() as f64).sum();
+        if s0 == 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "None of the features have neighbours; try a larger distance band or a different weights method.",
+            ));
+        }
+        let num_isolated = neighbours.iter().filter(|nb| nb.is_empty()).count();
+
+        if verbose {
+            println!("Calculating statistics...")
+        };
+        let mean = values.iter().sum::<f64>() / n as f64;
+        if threshold.is_nan() {
+            threshold = mean;
+        }
+        let is_black: Vec<bool> = values.iter().map(|&v| v >= threshold).collect();
+        let num_black = is_black.iter().filter(|&&b| b).count() as f64;
+        let observed_i = global_morans_i(&values, &neighbours);
+        let observed_c = gearys_c(&values, &neighbours);
+        let (observed_bb, observed_ww, observed_bw) = join_counts(&is_black, &neighbours);
+        let (expected_bb, expected_ww, expected_bw) = expected_join_counts(num_black, n as f64, s0);
+
+        let mut simulated = vec![vec![]; 5];
+        if num_permutations > 0 {
+            let values = Arc::new(values.clone());
+            let neighbours = Arc::new(neighbours.clone());
+            let configs = whitebox_common::configs::get_configs()?;
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads();
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let values = values.clone();
+                let neighbours = neighbours.clone();
+                let tx = tx.clone();
+                spawn(move || {
+                    let mut rng = SmallRng::from_entropy();
+                    let mut shuffled = values.to_vec();
+                    for _ in (0..num_permutations).filter(|p| p % num_procs == tid) {
+                        shuffled.shuffle(&mut rng);
+                        let is_black: Vec<bool> =
+                            shuffled.iter().map(|&v| v >= threshold).collect();
+                        let (bb, ww, bw) = join_counts(&is_black, &neighbours);
+                        if tx.send([
+                            global_morans_i(&shuffled, &neighbours),
+                            gearys_c(&shuffled, &neighbours),
+                            bb,
+                            ww,
+                            bw,
+                        ]).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+            for p in 0..num_permutations {
+                let stats = rx.recv().expect("Error receiving data from thread.");
+                for a in 0..5 {
+                    simulated[a].push(stats[a]);
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * (p + 1) as f64 / num_permutations as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Permutations", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        }
+
+        let results = vec![
+            StatisticResult::new("Moran's I", observed_i, -1f64 / (n as f64 - 1f64), &simulated[0]),
+            StatisticResult::new("Geary's C", observed_c, 1f64, &simulated[1]),
+            StatisticResult::new("Join count (BB)", observed_bb, expected_bb, &simulated[2]),
+            StatisticResult::new("Join count (WW)", observed_ww, expected_ww, &simulated[3]),
+            StatisticResult::new("Join count (BW)", observed_bw, expected_bw, &simulated[4]),
+        ];
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        let summary = vec![
+            ("Input file".to_string(), input_file.clone()),
+            ("Field".to_string(), field_name.clone()),
+            ("Number of features".to_string(), format!("{}", n)),
+            ("Features without neighbours".to_string(), format!("{}", num_isolated)),
+            ("Weights".to_string(), weights_type.description(distance, k)),
+            ("Number of joins".to_string(), format!("{}", s0 / 2f64)),
+            (
+                "Join count threshold".to_string(),
+                format!("{} ({} black features)", threshold, num_black),
+            ),
+            ("Number of permutations".to_string(), format!("{}", num_permutations)),
+        ];
+
+        if output_file.to_lowercase().ends_with(".json") {
+            let stats: Vec<serde_json::Value> = results.iter().map(|r| r.to_json()).collect();
+            let mut info = serde_json::Map::new();
+            for (key, value) in &summary {
+                info.insert(key.clone(), json!(value));
+            }
+            let report = json!({ "summary": info, "statistics": stats });
+            let f = File::create(output_file.clone())?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all(serde_json::to_string_pretty(&report)?.as_bytes())?;
+            let _ = writer.flush();
+            if verbose {
+                println!("Complete! Please see {} for output.", output_file);
+            }
+        } else {
+            write_html_report(&output_file, &summary, &results)?;
+            if verbose {
+                if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                    let output = Command::new("open")
+                        .arg(output_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "windows") {
+                    let output = Command::new("explorer.exe")
+                        .arg(output_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "linux") {
+                    let output = Command::new("xdg-open")
+                        .arg(output_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                }
+                println!("Complete! Please see {} for output.", output_file);
+            }
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum WeightsType {
+    Contiguity,
+    DistanceBand,
+    NearestNeighbours,
+}
+
+impl WeightsType {
+    fn description(&self, distance: f64, k: usize) -> String {
+        match self {
+            WeightsType::Contiguity => "Contiguity (shared vertices)".to_string(),
+            WeightsType::DistanceBand => format!("Distance band ({})", distance),
+            WeightsType::NearestNeighbours => format!("{} nearest neighbours (symmetric)", k),
+        }
+    }
+}
+
+struct StatisticResult {
+    name: String,
+    observed: f64,
+    expected: f64,
+    permutation_mean: f64,
+    permutation_sd: f64,
+    z_score: f64,
+    p_value: f64,
+}
+
+impl StatisticResult {
+    fn new(name: &str, observed: f64, expected: f64, simulated: &[f64]) -> StatisticResult {
+        let r = simulated.len() as f64;
+        let (mut mean, mut sd, mut z, mut p) = (f64::NAN, f64::NAN, f64::NAN, f64::NAN);
+        if r > 1f64 {
+            mean = simulated.iter().sum::<f64>() / r;
+            sd = (simulated.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / (r - 1f64))
+                .sqrt();
+            if sd > 0f64 {
+                z = (observed - mean) / sd;
+            }
+            p = permutation_p_value(observed, simulated);
+        }
+        StatisticResult {
+            name: name.to_string(),
+            observed: observed,
+            expected: expected,
+            permutation_mean: mean,
+            permutation_sd: sd,
+            z_score: z,
+            p_value: p,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let num = |v: f64| if v.is_finite() { json!(v) } else { serde_json::Value::Null };
+        json!({
+            "statistic": self.name,
+            "observed": num(self.observed),
+            "expected": num(self.expected),
+            "permutation_mean": num(self.permutation_mean),
+            "permutation_sd": num(self.permutation_sd),
+            "z_score": num(self.z_score),
+            "pseudo_p_value": num(self.p_value),
+        })
+    }
+}
+
+fn write_html_report(
+    output_file: &str,
+    summary: &[(String, String)],
+    results: &[StatisticResult],
+) -> Result<(), Error> {
+    let f = File::create(output_file)?;
+    let mut writer = BufWriter::new(f);
+
+    writer.write_all("<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+    <head>
+        <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+        <title>Global Spatial Autocorrelation</title>
+        <style  type=\"text/css\">
+            h1 {
+                font-size: 14pt;
+                margin-left: 15px;
+                margin-right: 15px;
+                text-align: center;
+                font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
+            }
+            p {
+                font-size: 12pt;
+                font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
+                margin-left: 15px;
+                margin-right: 15px;
+            }
+            caption {
+                font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
+                font-size: 12pt;
+                margin-left: 15px;
+                margin-right: 15px;
+            }
+            table {
+                font-size: 12pt;
+                font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
+                border-collapse: collapse;
+                align: center;
+            }
+            td, th {
+                border: 1px solid #222222;
+                text-align: centre;
+                padding: 8px;
+            }
+            tr:nth-child(even) {
+                background-color: #dddddd;
+            }
+            .numberCell {
+                text-align: right;
+            }
+        </style>
+    </head>
+    <body>
+        <h1>Global Spatial Autocorrelation Report</h1>
+    ".as_bytes())?;
+
+    for (key, value) in summary {
+        writer.write_all(&format!("<p><strong>{}</strong>: {}</p>", key, value).as_bytes())?;
+    }
+
+    writer.write_all("<div><table align=\"center\">".as_bytes())?;
+    writer.write_all("<caption>Global Statistics</caption>".as_bytes())?;
+    writer.write_all("<tr><th>Statistic</th><th>Observed</th><th>Expected</th><th>Permutation Mean</th><th>Permutation Std. Dev.</th><th>z-score</th><th>Pseudo p-value</th></tr>".as_bytes())?;
+    for r in results {
+        writer.write_all(
+            &format!(
+                "<tr><td>{}</td><td class=\"numberCell\">{:.5}</td><td class=\"numberCell\">{:.5}</td><td class=\"numberCell\">{:.5}</td><td class=\"numberCell\">{:.5}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td></tr>",
+                r.name, r.observed, r.expected, r.permutation_mean, r.permutation_sd, r.z_score, r.p_value
+            )
+            .as_bytes(),
+        )?;
+    }
+    writer.write_all("</table></div>".as_bytes())?;
+    writer.write_all("<p>The pseudo p-value is one-sided, in the direction of the deviation of the observed statistic from the permutation mean. Moran's I values greater than the expected value, and Geary's C values less than one, indicate positive spatial autocorrelation (clustering).</p>".as_bytes())?;
+    writer.write_all("</body>".as_bytes())?;
+
+    let _ = writer.flush();
+    Ok(())
+}
+
+/// Returns the location of a feature used for distance-based weights: the point itself for points,
+/// the centroid of the first part for polygons, and the middle vertex for lines.
+fn feature_location(record: &ShapefileGeometry, base_type: ShapeType) -> Point2D {
+    if base_type == ShapeType::Polygon {
+        let end = if record.num_parts > 1 {
+            record.parts[1] as usize
+        } else {
+            record.num_points as usize
+        };
+        let ring = &record.points[record.parts[0] as usize..end];
+        let mut area = 0f64;
+        let mut cx = 0f64;
+        let mut cy = 0f64;
+        for i in 0..ring.len() {
+            let p1 = ring[i];
+            let p2 = ring[(i + 1) % ring.len()];
+            let cross = p1.x * p2.y - p2.x * p1.y;
+            area += cross;
+            cx += (p1.x + p2.x) * cross;
+            cy += (p1.y + p2.y) * cross;
+        }
+        if area.abs() > 0f64 {
+            return Point2D::new(cx / (3f64 * area), cy / (3f64 * area));
+        }
+        let sum = ring
+            .iter()
+            .fold(Point2D::new(0f64, 0f64), |s, p| Point2D::new(s.x + p.x, s.y + p.y));
+        return Point2D::new(sum.x / ring.len() as f64, sum.y / ring.len() as f64);
+    } else if base_type == ShapeType::PolyLine {
+        return record.points[record.points.len() / 2];
+    }
+    record.points[0]
+}
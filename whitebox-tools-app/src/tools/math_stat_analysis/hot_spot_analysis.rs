@@ -0,0 +1,625 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use self::statrs::distribution::{Normal, Univariate};
+use whitebox_common::algorithms::{fdr_critical_p_value, local_gi_star, local_morans_i};
+use whitebox_common::structures::Point2D;
+use crate::tools::*;
+use whitebox_vector::*;
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use statrs;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool performs a local spatial autocorrelation (hot spot) analysis of a numeric attribute
+/// of a vector layer. Whereas global measures of spatial autocorrelation, such as Moran's *I*, describe
+/// the overall degree of clustering in a data set, local indicators of spatial association (LISA)
+/// identify *where* the clusters and outliers are located. Two local statistics are available
+/// (`--statistic`):
+///
+/// - **Getis-Ord Gi\*** (`gi_star`; the default) identifies statistically significant clusters of
+///   high values (hot spots) and low values (cold spots). The statistic is a z-score comparing the
+///   sum of the values within the neighbourhood of each feature, including the feature itself, with
+///   the expected sum.
+/// - **Local Moran's *I*** (`local_moran`; Anselin, 1995) identifies clusters of similar values (high-high
+///   and low-low) as well as spatial outliers (a high value surrounded by low values, or vice versa).
+///   The z-score is based on the expected value and variance of the statistic under the randomization
+///   assumption. Row-standardized weights are used.
+///
+/// The neighbours of each feature are determined by the `--weights` parameter. With `contiguity`
+/// weights, polygons and lines are neighbours if they share at least one vertex (i.e. queen's
+/// contiguity); this option is not available for point layers. With `distance` weights, the neighbours
+/// of a feature are all features within the distance band (`--distance`) of its location, and with
+/// `knn` weights, the neighbours are the *k* nearest features (`--k`). The location of a point is its
+/// coordinates, the location of a polygon is the centroid of its first part, and the location of a
+/// line is its middle vertex. Features with null attribute values are excluded from the analysis.
+///
+/// P-values are two-sided and are derived from the normal distribution. Because a separate test is
+/// performed for each feature, some features are expected to be significant by chance alone. When the
+/// `--fdr` flag is specified, significance is assessed using the Benjamini-Hochberg false discovery
+/// rate (FDR) correction, which controls the expected proportion of falsely significant features at
+/// the significance level (`--alpha`).
+///
+/// The output vector contains the input geometries and attributes, with the following additional
+/// fields: LOCAL_I (the Local Moran's *I*, only for the `local_moran` statistic), Z_SCORE, P_VALUE,
+/// CATEGORY, and NUM_NEIGH (the number of neighbours). The category is 'HOT SPOT', 'COLD SPOT' or
+/// 'NOT SIGNIFICANT' for the Gi\* statistic, and 'HH', 'LL', 'HL', 'LH' or 'NS' (not significant) for
+/// the Local Moran's *I*. Features that were excluded from the analysis, or that have no neighbours,
+/// have null values in the output fields.
+///
+/// # Reference
+/// Anselin, L. (1995). Local indicators of spatial association—LISA. Geographical Analysis, 27(2), 93-115.
+///
+/// Getis, A., & Ord, J. K. (1992). The analysis of spatial association by use of distance statistics.
+/// Geographical Analysis, 24(3), 189-206.
+///
+/// # See Also
+/// `ImageHotSpotAnalysis`, `ImageAutocorrelation`
+pub struct HotSpotAnalysis {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl HotSpotAnalysis {
+    pub fn new() -> HotSpotAnalysis {
+        // public constructor
+        let name = "HotSpotAnalysis".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description = "Performs a local spatial autocorrelation (Getis-Ord Gi* or Local Moran's I) analysis of a vector attribute.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Vector File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Field Name".to_owned(),
+            flags: vec!["--field".to_owned()],
+            description: "Numeric field to analyze.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Any,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Statistic".to_owned(),
+            flags: vec!["--statistic".to_owned()],
+            description: "Local statistic; options are 'gi_star' and 'local_moran'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "gi_star".to_owned(),
+                "local_moran".to_owned(),
+            ]),
+            default_value: Some("gi_star".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Spatial Weights".to_owned(),
+            flags: vec!["--weights".to_owned()],
+            description: "Method used to define neighbours; options are 'contiguity', 'distance' and 'knn'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "contiguity".to_owned(),
+                "distance".to_owned(),
+                "knn".to_owned(),
+            ]),
+            default_value: Some("distance".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Distance Band".to_owned(),
+            flags: vec!["--distance".to_owned()],
+            description: "Distance band used with distance weights.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Neighbours (k)".to_owned(),
+            flags: vec!["--k".to_owned()],
+            description: "Number of nearest neighbours used with knn weights.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("8".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Significance Level (alpha)".to_owned(),
+            flags: vec!["--alpha".to_owned()],
+            description: "Significance level used to categorize features.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.05".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Apply false discovery rate (FDR) correction?".to_owned(),
+            flags: vec!["--fdr".to_owned()],
+            description: "Apply the Benjamini-Hochberg false discovery rate correction.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=counties.shp --field=RATE -o=hot_spots.shp --statistic=gi_star --weights=contiguity --fdr",
+            short_exe, name
+        ).replace("*", &sep);
+
+        HotSpotAnalysis {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for HotSpotAnalysis {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let field_name = parsed.get_string("field").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut statistic = String::from("gi_star");
+        let mut weights = String::from("distance");
+        let distance = parsed.get_f64("distance").unwrap_or(0f64);
+        let k = parsed.get_usize("k")?.unwrap_or(8usize);
+        let alpha = parsed.get_f64("alpha").unwrap_or(0.05f64);
+        let use_fdr = parsed.get_bool("fdr");
+
+        // read the arguments
+        if let Some(value) = parsed.get_string("statistic") {
+            statistic = value.to_lowercase();
+        }
+        if let Some(value) = parsed.get_string("weights") {
+            weights = value.to_lowercase();
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        let start = Instant::now();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        let is_moran = statistic.contains("moran");
+        let weights_type = if weights.contains("contig") {
+            WeightsType::Contiguity
+        } else if weights.contains("knn") || weights.contains("nearest") {
+            WeightsType::NearestNeighbours
+        } else {
+            WeightsType::DistanceBand
+        };
+        if weights_type == WeightsType::DistanceBand && distance <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "A positive distance band (--distance) must be specified when using distance weights.",
+            ));
+        }
+        if weights_type == WeightsType::NearestNeighbours && k == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of neighbours (--k) must be greater than zero.",
+            ));
+        }
+        if alpha <= 0f64 || alpha >= 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The significance level (--alpha) must be between 0 and 1.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Shapefile::read(&input_file)?;
+        let base_type = input.header.shape_type.base_shape_type();
+        if weights_type == WeightsType::Contiguity
+            && (base_type == ShapeType::Point || base_type == ShapeType::MultiPoint)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Contiguity weights cannot be used with point data; use distance or knn weights instead.",
+            ));
+        }
+        match input.attributes.get_field_num(&field_name) {
+            Some(i) if input.attributes.is_field_numeric(i) => {}
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The specified field must be a numeric field of the input attribute table.",
+                ))
+            }
+        }
+
+        // read the observations; features with null values are excluded
+        let mut record_nums = vec![];
+        let mut obs_num = vec![None; input.num_records];
+        let mut values = vec![];
+        let mut locations = vec![];
+        for record_num in 0..input.num_records {
+            reporter.check_cancelled()?;
+            let record = input.get_record(record_num);
+            if record.shape_type == ShapeType::Null || record.num_points == 0 {
+                continue;
+            }
+            let value = match input.attributes.get_value(record_num, &field_name) {
+                FieldData::Int(v) => v as f64,
+                FieldData::Real(v) => v,
+                _ => f64::NAN,
+            };
+            if !value.is_finite() {
+                continue;
+            }
+            obs_num[record_num] = Some(values.len());
+            record_nums.push(record_num);
+            values.push(value);
+            locations.push(feature_location(record, base_type));
+        }
+        let n = values.len();
+        if n < 3 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input file must contain at least three features with non-null values.",
+            ));
+        }
+
+        if verbose {
+            println!("Finding neighbours...")
+        };
+        let mut neighbours: Vec<Vec<(usize, f64)>> = vec![vec![]; n];
+        match weights_type {
+            WeightsType::Contiguity => {
+                let mut vertex_map: HashMap<(u64, u64), Vec<usize>> = HashMap::new();
+                for i in 0..n {
+                    let record = input.get_record(record_nums[i]);
+                    for p in &record.points {
+                        let entry = vertex_map.entry((p.x.to_bits(), p.y.to_bits())).or_insert(vec![]);
+                        if entry.last() != Some(&i) {
+                            entry.push(i);
+                        }
+                    }
+                }
+                for i in 0..n {
+                    let record = input.get_record(record_nums[i]);
+                    let mut set = HashSet::new();
+                    for p in &record.points {
+                        for &j in &vertex_map[&(p.x.to_bits(), p.y.to_bits())] {
+                            if j != i && set.insert(j) {
+                                neighbours[i].push((j, 1f64));
+                            }
+                        }
+                    }
+                    reporter.check_cancelled()?;
+                    if verbose {
+                        progress = (100.0_f64 * (i + 1) as f64 / n as f64) as usize;
+                        if progress != old_progress {
+                            reporter.progress("Progress", progress);
+                            old_progress = progress;
+                        }
+                    }
+                }
+            }
+            _ => {
+                let mut tree = KdTree::with_capacity(2, 64);
+                for i in 0..n {
+                    tree.add([locations[i].x, locations[i].y], i).unwrap();
+                }
+                for i in 0..n {
+                    let p = locations[i];
+                    let ret = if weights_type == WeightsType::DistanceBand {
+                        tree.within(&[p.x, p.y], distance * distance, &squared_euclidean)
+                            .unwrap()
+                    } else {
+                        tree.nearest(&[p.x, p.y], (k + 1).min(n), &squared_euclidean)
+                            .unwrap()
+                    };
+                    for (_, &j) in ret.iter().filter(|(_, &j)| j != i) {
+                        if weights_type == WeightsType::DistanceBand
+                            || neighbours[i].len() < k
+                        {
+                            neighbours[i].push((j, 1f64));
+                        }
+                    }
+                    reporter.check_cancelled()?;
+                    if verbose {
+                        progress = (100.0_f64 * (i + 1) as f64 / n as f64) as usize;
+                        if progress != old_progress {
+                            reporter.progress("Progress", progress);
+                            old_progress = progress;
+                        }
+                    }
+                }
+            }
+        }
+
+        if is_moran {
+            // row-standardize the weights
+            for nb in neighbours.iter_mut() {
+                let num_neighbours = nb.len() as f64;
+                for w in nb.iter_mut() {
+                    w.1 = 1f64 / num_neighbours;
+                }
+            }
+        }
+
+        if verbose {
+            println!("Calculating local statistics...")
+        };
+        let (local_i, z_scores): (Vec<f64>, Vec<f64>) = if is_moran {
+            local_morans_i(&values, &neighbours).into_iter().unzip()
+        } else {
+            (vec![0f64; n], local_gi_star(&values, &neighbours))
+        };
+        let distribution = Normal::new(0.0, 1.0).unwrap();
+        let p_values: Vec<f64> = (0..n)
+            .map(|i| {
+                if neighbours[i].is_empty() {
+                    f64::NAN
+                } else {
+                    2f64 * (1f64 - distribution.cdf(z_scores[i].abs()))
+                }
+            })
+            .collect();
+        let critical_p = if use_fdr {
+            fdr_critical_p_value(&p_values, alpha)
+        } else {
+            alpha
+        };
+        let mean = values.iter().sum::<f64>() / n as f64;
+
+        // create output file
+        let mut output = Shapefile::initialize_using_file(
+            &output_file,
+            &input,
+            input.header.shape_type,
+            true,
+        )?;
+        if is_moran {
+            output
+                .attributes
+                .add_field(&AttributeField::new("LOCAL_I", FieldDataType::Real, 18u8, 6u8));
+        }
+        output
+            .attributes
+            .add_field(&AttributeField::new("Z_SCORE", FieldDataType::Real, 12u8, 4u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("P_VALUE", FieldDataType::Real, 12u8, 6u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("CATEGORY", FieldDataType::Text, 15u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("NUM_NEIGH", FieldDataType::Int, 7u8, 0u8));
+
+        let num_null = if is_moran { 5 } else { 4 };
+        let mut num_significant = 0;
+        for record_num in 0..input.num_records {
+            reporter.check_cancelled()?;
+            let record = input.get_record(record_num);
+            output.add_record(record.clone());
+            let mut atts = input.attributes.get_record(record_num);
+            match obs_num[record_num] {
+                Some(i) if !neighbours[i].is_empty() => {
+                    let significant = p_values[i] <= critical_p;
+                    if significant {
+                        num_significant += 1;
+                    }
+                    let category = if is_moran {
+                        if !significant {
+                            "NS"
+                        } else if local_i[i] > 0f64 {
+                            if values[i] > mean {
+                                "HH"
+                            } else {
+                                "LL"
+                            }
+                        } else if values[i] > mean {
+                            "HL"
+                        } else {
+                            "LH"
+                        }
+                    } else if !significant {
+                        "NOT SIGNIFICANT"
+                    } else if z_scores[i] > 0f64 {
+                        "HOT SPOT"
+                    } else {
+                        "COLD SPOT"
+                    };
+                    if is_moran {
+                        atts.push(FieldData::Real(local_i[i]));
+                    }
+                    atts.push(FieldData::Real(z_scores[i]));
+                    atts.push(FieldData::Real(p_values[i]));
+                    atts.push(FieldData::Text(category.to_string()));
+                    atts.push(FieldData::Int(neighbours[i].len() as i32));
+                }
+                Some(_) => {
+                    for _ in 0..num_null - 1 {
+                        atts.push(FieldData::Null);
+                    }
+                    atts.push(FieldData::Int(0));
+                }
+                None => {
+                    for _ in 0..num_null {
+                        atts.push(FieldData::Null);
+                    }
+                }
+            }
+            output.attributes.add_record(atts, false);
+        }
+
+        if verbose {
+            println!(
+                "Number of significant features: {} of {}{}",
+                num_significant,
+                n,
+                if use_fdr {
+                    format!(" (FDR critical p-value: {:.6})", critical_p)
+                } else {
+                    String::new()
+                }
+            );
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("{}", &format!("Elapsed Time: {}", elapsed_time));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum WeightsType {
+    Contiguity,
+    DistanceBand,
+    NearestNeighbours,
+}
+
+/// Returns the location of a feature used for distance-based weights: the point itself for points,
+/// the centroid of the first part for polygons, and the middle vertex for lines.
+fn feature_location(record: &ShapefileGeometry, base_type: ShapeType) -> Point2D {
+    if base_type == ShapeType::Polygon {
+        let end = if record.num_parts > 1 {
+            record.parts[1] as usize
+        } else {
+            record.num_points as usize
+        };
+        let ring = &record.points[record.parts[0] as usize..end];
+        let mut area = 0f64;
+        let mut cx = 0f64;
+        let mut cy = 0f64;
+        for i in 0..ring.len() {
+            let p1 = ring[i];
+            let p2 = ring[(i + 1) % ring.len()];
+            let cross = p1.x * p2.y - p2.x * p1.y;
+            area += cross;
+            cx += (p1.x + p2.x) * cross;
+            cy += (p1.y + p2.y) * cross;
+        }
+        if area.abs() > 0f64 {
+            return Point2D::new(cx / (3f64 * area), cy / (3f64 * area));
+        }
+        let sum = ring
+            .iter()
+            .fold(Point2D::new(0f64, 0f64), |s, p| Point2D::new(s.x + p.x, s.y + p.y));
+        return Point2D::new(sum.x / ring.len() as f64, sum.y / ring.len() as f64);
+    } else if base_type == ShapeType::PolyLine {
+        return record.points[record.points.len() / 2];
+    }
+    record.points[0]
+}
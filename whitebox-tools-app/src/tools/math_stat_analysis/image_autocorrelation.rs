@@ -9,19 +9,18 @@ License: MIT
 use self::statrs::distribution::{Normal, Univariate};
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use statrs;
 use std::env;
 use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// Spatial autocorrelation describes the extent to which a variable is either dispersed or clustered through space.
 /// In the case of a raster image, spatial autocorrelation refers to the similarity in the values of nearby grid
@@ -186,46 +185,15 @@ impl WhiteboxTool for ImageAutocorrelation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files: String = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files: String = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut contiguity = String::new();
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                if keyval {
-                    input_files = vec[1].to_string();
-                } else {
-                    input_files = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-contiguity" {
-                if keyval {
-                    contiguity = vec[1].to_string().to_lowercase();
-                } else {
-                    contiguity = args[i + 1].to_string().to_lowercase();
-                }
-            }
+        if let Some(value) = parsed.get_string("contiguity") {
+            contiguity = value.to_string().to_lowercase();
         }
 
         if verbose {
@@ -239,8 +207,6 @@ impl WhiteboxTool for ImageAutocorrelation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -273,18 +239,14 @@ impl WhiteboxTool for ImageAutocorrelation {
             extension.push_str(ext);
             output_file = files_vec[0].replace(&extension, ".html");
         }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut file_names = vec![];
         for a in 0..files_vec.len() {
             let value = files_vec[a];
             if !value.trim().is_empty() {
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 file_names.push(input_file);
             }
         }
@@ -293,12 +255,8 @@ impl WhiteboxTool for ImageAutocorrelation {
 
         let distribution = Normal::new(0.0, 1.0).unwrap();
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
 
         let mut image_totals = vec![0f64; num_files];
@@ -329,30 +287,34 @@ impl WhiteboxTool for ImageAutocorrelation {
             } else {
                 if input.configs.columns as isize != columns || input.configs.rows as isize != rows
                 {
-                    return Err(Error::new(
-                        ErrorKind::InvalidInput,
-                        "All input images must have the same dimensions (rows and columns).",
-                    ));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must have the same dimensions (rows and columns)."));
                 }
             }
 
+            let token = reporter.cancellation_token();
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                let token = token.clone();
+                spawn(move || {
                     let mut total = 0f64;
                     let mut n = 0f64;
                     let mut z: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        if token.is_cancelled() {
+                            break;
+                        }
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 total += z;
                                 n += 1f64;
                             }
                         }
                     }
-                    tx.send((total, n)).unwrap();
+                    if tx.send((total, n)).is_err() {
+                        return;
+                    }
                 });
             }
             for np in 0..num_procs {
@@ -360,10 +322,11 @@ impl WhiteboxTool for ImageAutocorrelation {
                 image_totals[a] += total;
                 n[a] += image_n;
 
+                reporter.check_cancelled()?;
                 if verbose && num_procs > 1 {
                     progress = (100.0_f64 * np as f64 / (num_procs - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress (Loop 1 of 2): {}%", progress);
+                        reporter.progress("Progress (Loop 1 of 2)", progress);
                         old_progress = progress;
                     }
                 }
@@ -385,7 +348,7 @@ impl WhiteboxTool for ImageAutocorrelation {
             for row in 0..rows {
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         total_deviation += (z - mean[a]) * (z - mean[a]);
                         k += (z - mean[a]) * (z - mean[a]) * (z - mean[a]) * (z - mean[a]);
                         wij = 0f64;
@@ -393,7 +356,7 @@ impl WhiteboxTool for ImageAutocorrelation {
                             x = col + dx[i];
                             y = row + dy[i];
                             zn = input.get_value(y, x);
-                            if zn != nodata {
+                            if !is_nodata(zn, nodata) {
                                 w += 1f64;
                                 numerator += (z - mean[a]) * (zn - mean[a]);
                                 wij += 1f64;
@@ -402,10 +365,11 @@ impl WhiteboxTool for ImageAutocorrelation {
                         s2 += wij * wij;
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress (Loop 2 of 2): {}%", progress);
+                        reporter.progress("Progress (Loop 2 of 2)", progress);
                         old_progress = progress;
                     }
                 }
@@ -436,10 +400,11 @@ impl WhiteboxTool for ImageAutocorrelation {
             z_r[a] = (i[a] - e_i[a]) / var_randomization[a].sqrt();
             p_value_r[a] = 2f64 * (1f64 - distribution.cdf(z_r[a].abs()));
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * a as f64 / (num_files - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Loop {} of {}: {}%", (a + 1), files_vec.len(), progress);
+                    reporter.progress(&format!("Loop {} of {}", (a + 1), files_vec.len()), progress);
                     old_progress = progress;
                 }
             }
@@ -9,18 +9,17 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::rendering::html::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to estimate the Pearson product-moment correlation coefficient (*r*) between two or
 /// more input images (`--inputs`). The *r*-value is a measure of the linear association in the variation of
@@ -152,40 +151,11 @@ impl WhiteboxTool for ImageCorrelation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files: String = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                input_files = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files: String = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -198,8 +168,6 @@ impl WhiteboxTool for ImageCorrelation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -220,30 +188,22 @@ impl WhiteboxTool for ImageCorrelation {
             extension.push_str(ext);
             output_file = files_vec[0].replace(&extension, ".html");
         }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let mut file_names = vec![];
         for a in 0..files_vec.len() {
             let value = files_vec[a];
             if !value.trim().is_empty() {
                 let mut input_file = value.trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 file_names.push(input_file);
             }
         }
 
         let num_files = file_names.len();
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
 
         let mut image_totals = vec![0f64; num_files];
@@ -266,24 +226,26 @@ impl WhiteboxTool for ImageCorrelation {
             } else {
                 if input.configs.columns as isize != columns || input.configs.rows as isize != rows
                 {
-                    return Err(Error::new(
-                        ErrorKind::InvalidInput,
-                        "All input images must have the same dimensions (rows and columns).",
-                    ));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must have the same dimensions (rows and columns)."));
                 }
             }
 
+            let token = reporter.cancellation_token();
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                let token = token.clone();
+                spawn(move || {
                     let mut total = 0f64;
                     let mut n = 0f64;
                     let mut z: f64;
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        if token.is_cancelled() {
+                            break;
+                        }
                         for col in 0..columns {
                             z = input[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 // image_totals[a] += z;
                                 // image_n[a] += 1f64;
                                 total += z;
@@ -291,7 +253,9 @@ impl WhiteboxTool for ImageCorrelation {
                             }
                         }
                     }
-                    tx.send((total, n)).unwrap();
+                    if tx.send((total, n)).is_err() {
+                        return;
+                    }
                 });
             }
             for _ in 0..num_procs {
@@ -301,15 +265,11 @@ impl WhiteboxTool for ImageCorrelation {
             }
             image_averages[a] = image_totals[a] / image_n[a];
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * a as f64 / (num_files - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!(
-                        "Calculating image averages ({} of {}): {}%",
-                        (a + 1),
-                        files_vec.len(),
-                        progress
-                    );
+                    reporter.progress(&format!("Calculating image averages ({} of {})", (a + 1), files_vec.len()), progress);
                     old_progress = progress;
                 }
             }
@@ -332,22 +292,27 @@ impl WhiteboxTool for ImageCorrelation {
                     let nodata2 = image2.configs.nodata;
 
                     let (tx, rx) = mpsc::channel();
+                    let token = reporter.cancellation_token();
                     for tid in 0..num_procs {
                         let image1 = image1.clone();
                         let image2 = image2.clone();
                         let image_averages = image_averages.clone();
                         let tx = tx.clone();
-                        thread::spawn(move || {
+                        let token = token.clone();
+                        spawn(move || {
                             let mut z1: f64;
                             let mut z2: f64;
                             let mut image1_total_deviation = 0f64;
                             let mut image2_total_deviation = 0f64;
                             let mut total_product_deviations = 0f64;
                             for row in (0..rows).filter(|r| r % num_procs == tid) {
+                                if token.is_cancelled() {
+                                    break;
+                                }
                                 for col in 0..columns {
                                     z1 = image1[(row, col)];
                                     z2 = image2[(row, col)];
-                                    if z1 != nodata1 && z2 != nodata2 {
+                                    if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                                         image1_total_deviation +=
                                             (z1 - image_averages[a]) * (z1 - image_averages[a]);
                                         image2_total_deviation +=
@@ -357,12 +322,13 @@ impl WhiteboxTool for ImageCorrelation {
                                     }
                                 }
                             }
-                            tx.send((
+                            if tx.send((
                                 image1_total_deviation,
                                 image2_total_deviation,
                                 total_product_deviations,
-                            ))
-                            .unwrap();
+                            )).is_err() {
+                                return;
+                            }
                         });
                     }
                     let mut image1_total_deviation = 0f64;
@@ -375,21 +341,18 @@ impl WhiteboxTool for ImageCorrelation {
                         image2_total_deviation += val2;
                         total_product_deviations += val3;
                     }
+                    reporter.check_cancelled()?;
                     correlation_matrix[a][b] = total_product_deviations
                         / (image1_total_deviation * image2_total_deviation).sqrt();
                 }
             }
             i += 1;
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * a as f64 / (num_files - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!(
-                        "Calculating the correlation matrix ({} of {}): {}%",
-                        (a + 1),
-                        files_vec.len(),
-                        progress
-                    );
+                    reporter.progress(&format!("Calculating the correlation matrix ({} of {})", (a + 1), files_vec.len()), progress);
                     old_progress = progress;
                 }
             }
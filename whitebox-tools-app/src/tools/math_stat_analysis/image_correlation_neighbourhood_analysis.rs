@@ -12,11 +12,11 @@ use statrs::distribution::{StudentsT, Univariate};
 use std::cmp::Ordering::Equal;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to perform nieghbourhood-based (i.e. using roving search windows applied to each
 /// grid cell) correlation analysis on two input rasters (`--input1` and `--input2`). The tool outputs a
@@ -177,81 +177,25 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1 = String::new();
-        let mut input_file2 = String::new();
-        let mut output_file1 = String::new();
-        let mut output_file2 = String::new();
-        let mut filter_size = 11usize;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file1 = parsed.get_string("output1").unwrap_or_default();
+        let mut output_file2 = parsed.get_string("output2").unwrap_or_default();
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(11usize);
         let mut stat_type = String::from("pearson");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i1" || flag_val == "-input1" {
-                input_file1 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-i2" || flag_val == "-input2" {
-                input_file2 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o1" || flag_val == "-output1" {
-                output_file1 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o2" || flag_val == "-output2" {
-                output_file2 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-stat" {
-                let val = if keyval {
-                    vec[1].to_lowercase()
-                } else {
-                    args[i + 1].to_lowercase()
-                };
-                stat_type = if val.contains("son") {
-                    "pearson".to_string()
-                } else if val.contains("kendall") {
-                    "kendall".to_string()
-                } else {
-                    "spearman".to_string()
-                };
-            } else if vec[0].to_lowercase() == "-filter" || vec[0].to_lowercase() == "--filter" {
-                filter_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
+        if let Some(value) = parsed.get_string("stat") {
+            let val = value.to_lowercase();
+            stat_type = if val.contains("son") {
+                "pearson".to_string()
+            } else if val.contains("kendall") {
+                "kendall".to_string()
+            } else {
+                "spearman".to_string()
+            };
         }
 
         if verbose {
@@ -268,7 +212,6 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
         if filter_size < 3 {
             filter_size = 3;
         }
-        let sep: String = path::MAIN_SEPARATOR.to_string();
 
         let mut progress: usize;
         let mut old_progress: usize = 1;
@@ -276,27 +219,15 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
         //let start = time::now();
         let start = Instant::now(); // had to change to this from old time::now
 
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
 
-        if !output_file1.contains(&sep) && !output_file1.contains("/") {
-            output_file1 = format!("{}{}", working_directory, output_file1);
-        }
+        output_file1 = resolve_path(&output_file1, working_directory);
 
-        if !output_file2.contains(&sep) && !output_file2.contains("/") {
-            output_file2 = format!("{}{}", working_directory, output_file2);
-        }
+        output_file2 = resolve_path(&output_file2, working_directory);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         if verbose {
             println!("Reading data...")
@@ -325,7 +256,7 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                 let image1 = image1.clone();
                 let image2 = image2.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut num_cells: usize;
                     let mut sum1: f64;
                     let mut sum2: f64;
@@ -360,7 +291,7 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                         for col in 0..columns {
                             z1 = image1.get_value(row, col);
                             z2 = image2.get_value(row, col);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                                 // First, calculate the mean
                                 num_cells = 0;
                                 sum1 = 0f64;
@@ -368,7 +299,7 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                                 for i in 0..num_pixels_in_filter {
                                     z_n1 = image1.get_value(row + dy[i], col + dx[i]);
                                     z_n2 = image2.get_value(row + dy[i], col + dx[i]);
-                                    if z_n1 != nodata1 && z_n2 != nodata2 {
+                                    if !is_nodata(z_n1, nodata1) && !is_nodata(z_n2, nodata2) {
                                         sum1 += z_n1;
                                         sum2 += z_n2;
                                         num_cells += 1;
@@ -385,7 +316,7 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                                     for i in 0..num_pixels_in_filter {
                                         z_n1 = image1.get_value(row + dy[i], col + dx[i]);
                                         z_n2 = image2.get_value(row + dy[i], col + dx[i]);
-                                        if z_n1 != nodata1 && z_n2 != nodata2 {
+                                        if !is_nodata(z_n1, nodata1) && !is_nodata(z_n2, nodata2) {
                                             total_deviation1 += (z_n1 - mean1) * (z_n1 - mean1);
                                             total_deviation2 += (z_n2 - mean2) * (z_n2 - mean2);
                                             product_deviations += (z_n1 - mean1) * (z_n2 - mean2);
@@ -418,7 +349,9 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                                 }
                             }
                         }
-                        tx.send((row, data1, data2)).unwrap();
+                        if tx.send((row, data1, data2)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -428,10 +361,11 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                 output_val.set_row_data(row, data1);
                 output_sig.set_row_data(row, data2);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Performing Correlation: {}%", progress);
+                        reporter.progress("Performing Correlation", progress);
                         old_progress = progress;
                     }
                 }
@@ -443,7 +377,7 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                 let image1 = image1.clone();
                 let image2 = image2.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut num_cells: usize;
                     let mut num_cells_f64: f64;
                     let mut tau: f64;
@@ -480,14 +414,14 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                         for col in 0..columns {
                             z1 = image1.get_value(row, col);
                             z2 = image2.get_value(row, col);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                                 let mut v1 = Vec::with_capacity(num_pixels_in_filter);
                                 let mut v2 = Vec::with_capacity(num_pixels_in_filter);
                                 num_cells = 0;
                                 for i in 0..num_pixels_in_filter {
                                     z_n1 = image1.get_value(row + dy[i], col + dx[i]);
                                     z_n2 = image2.get_value(row + dy[i], col + dx[i]);
-                                    if z_n1 != nodata1 && z_n2 != nodata2 {
+                                    if !is_nodata(z_n1, nodata1) && !is_nodata(z_n2, nodata2) {
                                         num_cells += 1;
                                         // tuple = (value, index, rank)
                                         v1.push((z_n1, num_cells, 0f64));
@@ -610,7 +544,9 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                                 }
                             }
                         }
-                        tx.send((row, data1, data2)).unwrap();
+                        if tx.send((row, data1, data2)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -620,10 +556,11 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                 output_val.set_row_data(row, data1);
                 output_sig.set_row_data(row, data2);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Performing Correlation: {}%", progress);
+                        reporter.progress("Performing Correlation", progress);
                         old_progress = progress;
                     }
                 }
@@ -635,7 +572,7 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                 let image1 = image1.clone();
                 let image2 = image2.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut num_cells: usize;
                     let mut num_cells_f64: f64;
                     let mut rho: f64;
@@ -669,14 +606,14 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                         for col in 0..columns {
                             z1 = image1.get_value(row, col);
                             z2 = image2.get_value(row, col);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                                 let mut v1 = Vec::with_capacity(num_pixels_in_filter);
                                 let mut v2 = Vec::with_capacity(num_pixels_in_filter);
                                 num_cells = 0;
                                 for i in 0..num_pixels_in_filter {
                                     z_n1 = image1.get_value(row + dy[i], col + dx[i]);
                                     z_n2 = image2.get_value(row + dy[i], col + dx[i]);
-                                    if z_n1 != nodata1 && z_n2 != nodata2 {
+                                    if !is_nodata(z_n1, nodata1) && !is_nodata(z_n2, nodata2) {
                                         num_cells += 1;
                                         // tuple = (value, index, rank)
                                         v1.push((z_n1, num_cells, 0f64));
@@ -783,8 +720,9 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                                 }
                             }
                         }
-                        tx.send((row, data1, data2, num_ties, max_num_ties))
-                            .unwrap();
+                        if tx.send((row, data1, data2, num_ties, max_num_ties)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -801,10 +739,11 @@ impl WhiteboxTool for ImageCorrelationNeighbourhoodAnalysis {
                     max_ties = max_row_ties;
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Performing Correlation: {}%", progress);
+                        reporter.progress("Performing Correlation", progress);
                         old_progress = progress;
                     }
                 }
@@ -0,0 +1,585 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::{expected_join_counts, permutation_p_value};
+use whitebox_common::structures::Array2D;
+use whitebox_raster::*;
+use crate::tools::*;
+use rand::prelude::*;
+use rand::rngs::SmallRng;
+use serde_json::json;
+use std::env;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool measures the global spatial autocorrelation of a raster image using Moran's *I*, Geary's *C*,
+/// and join count statistics, and evaluates their significance using a Monte Carlo permutation test. It
+/// complements the `ImageAutocorrelation` tool, which reports Moran's *I* with analytical significance
+/// tests based on the normality and randomization assumptions.
+///
+/// Moran's *I* ranges from approximately -1 (dispersed) to 1 (clustered). Geary's *C* is more sensitive to
+/// differences between neighbouring cells; values less than one indicate positive spatial autocorrelation.
+/// The join count statistics (black-black, white-white, and black-white) describe the autocorrelation of a
+/// binary image. Grid cells are classed as 'black' if their value is greater than or equal to the threshold
+/// (`--threshold`), and 'white' otherwise; by default, the threshold is the image mean, such that binary
+/// (0/1) images are handled appropriately.
+///
+/// The neighbours of each grid cell are defined by the contiguity type (`--contiguity`; Rook's, King's, or
+/// Bishop's), as in the `ImageAutocorrelation` tool. For the permutation test, the valid grid cell values
+/// are randomly reassigned among the valid grid cells a number of times (`--permutations`) and the statistics
+/// are recalculated for each permutation. The pseudo p-value is the proportion of the permutations (plus the
+/// observed arrangement) with a statistic at least as extreme as the observed value. Notice that the smallest
+/// attainable p-value is 1 / (permutations + 1) and that each permutation requires a full pass through the
+/// image, such that the analysis of large images with many permutations can be time consuming.
+///
+/// The output report (`--output`) is an HTML file, unless the file name has a *.json* extension, in which case
+/// the results are written in JSON format.
+///
+/// # See Also
+/// `GlobalAutocorrelation`, `ImageAutocorrelation`, `ImageHotSpotAnalysis`
+pub struct ImageGlobalAutocorrelation {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ImageGlobalAutocorrelation {
+    pub fn new() -> ImageGlobalAutocorrelation {
+        // public constructor
+        let name = "ImageGlobalAutocorrelation".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description = "Calculates Moran's I, Geary's C, and join count statistics for a raster, with permutation inference.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Report File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output HTML or JSON report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Contiguity Type".to_owned(),
+            flags: vec!["--contiguity".to_owned()],
+            description: "Contiguity type.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "Rook".to_owned(),
+                "King".to_owned(),
+                "Bishop".to_owned(),
+            ]),
+            default_value: Some("Rook".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Permutations".to_owned(),
+            flags: vec!["--permutations".to_owned()],
+            description: "Number of random permutations used to evaluate significance.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("99".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Join Count Threshold".to_owned(),
+            flags: vec!["--threshold".to_owned()],
+            description: "Values greater than or equal to the threshold are 'black' in the join count analysis; the default is the mean.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=image.tif -o=report.json --contiguity=King --permutations=199",
+            short_exe, name
+        ).replace("*", &sep);
+
+        ImageGlobalAutocorrelation {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ImageGlobalAutocorrelation {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut contiguity = String::from("rook");
+        let num_permutations = parsed.get_usize("permutations")?.unwrap_or(99usize);
+        let mut threshold = parsed.get_f64("threshold").unwrap_or(f64::NAN);
+
+        // read the arguments
+        if let Some(value) = parsed.get_string("contiguity") {
+            contiguity = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        let (dx, dy) = if contiguity.contains("bishop") {
+            (vec![1, 1, -1, -1], vec![-1, 1, 1, -1])
+        } else if contiguity.contains("queen") || contiguity.contains("king") {
+            (
+                vec![1, 1, 1, 0, -1, -1, -1, 0],
+                vec![-1, 0, 1, 1, 1, 0, -1, -1],
+            )
+        } else {
+            // go with the rook default
+            (vec![1, 0, -1, 0], vec![0, 1, 0, -1])
+        };
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Raster::new(&input_file, "r")?;
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let start = Instant::now();
+
+        let mut grid: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut values = vec![];
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            let data = input.get_row_data(row);
+            for col in 0..columns as usize {
+                if !is_nodata(data[col], nodata) {
+                    values.push(data[col]);
+                }
+            }
+            grid.set_row_data(row, data);
+        }
+        drop(input);
+        let n = values.len() as f64;
+        if n < 4f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input image must contain at least four valid grid cells.",
+            ));
+        }
+        let mean = values.iter().sum::<f64>() / n;
+        let total_deviation = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>();
+        if threshold.is_nan() {
+            threshold = mean;
+        }
+        let num_black = values.iter().filter(|&&v| v >= threshold).count() as f64;
+
+        if verbose {
+            println!("Calculating statistics...")
+        };
+        let sums = neighbour_sums(&grid, &dx, &dy, mean, threshold);
+        let s0 = sums[2];
+        if s0 == 0f64 || total_deviation == 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input image does not contain any neighbouring valid grid cells with variable values.",
+            ));
+        }
+        let to_statistics = |sums: [f64; 5]| {
+            [
+                n / s0 * sums[0] / total_deviation,
+                (n - 1f64) * sums[1] / (2f64 * s0 * total_deviation),
+                sums[3] / 2f64,
+                (s0 - sums[3] - sums[4]) / 2f64,
+                sums[4] / 2f64,
+            ]
+        };
+        let observed = to_statistics(sums);
+
+        let mut simulated = vec![vec![]; 5];
+        if num_permutations > 0 {
+            let grid = Arc::new(grid);
+            let values = Arc::new(values);
+            let dx = Arc::new(dx);
+            let dy = Arc::new(dy);
+            let configs = whitebox_common::configs::get_configs()?;
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).max_threads(num_permutations).num_threads();
+            let (tx, rx) = mpsc::channel();
+            for tid in 0..num_procs {
+                let grid = grid.clone();
+                let values = values.clone();
+                let dx = dx.clone();
+                let dy = dy.clone();
+                let tx = tx.clone();
+                spawn(move || {
+                    let mut rng = SmallRng::from_entropy();
+                    let mut shuffled = values.to_vec();
+                    let mut permuted = grid.duplicate();
+                    for _ in (0..num_permutations).filter(|p| p % num_procs == tid) {
+                        shuffled.shuffle(&mut rng);
+                        let mut i = 0;
+                        for row in 0..rows {
+                            for col in 0..columns {
+                                if !is_nodata(grid.get_value(row, col), nodata) {
+                                    permuted.set_value(row, col, shuffled[i]);
+                                    i += 1;
+                                }
+                            }
+                        }
+                        if tx.send(neighbour_sums(&permuted, &dx, &dy, mean, threshold)).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+            for p in 0..num_permutations {
+                let sums = rx.recv().expect("Error receiving data from thread.");
+                let stats = to_statistics(sums);
+                for a in 0..5 {
+                    simulated[a].push(stats[a]);
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * (p + 1) as f64 / num_permutations as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Permutations", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        }
+
+        let (expected_bb, expected_ww, expected_bw) = expected_join_counts(num_black, n, s0);
+        let results = vec![
+            StatisticResult::new("Moran's I", observed[0], -1f64 / (n - 1f64), &simulated[0]),
+            StatisticResult::new("Geary's C", observed[1], 1f64, &simulated[1]),
+            StatisticResult::new("Join count (BB)", observed[2], expected_bb, &simulated[2]),
+            StatisticResult::new("Join count (WW)", observed[3], expected_ww, &simulated[3]),
+            StatisticResult::new("Join count (BW)", observed[4], expected_bw, &simulated[4]),
+        ];
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        let contiguity_name = if contiguity.contains("bishop") {
+            "Bishop's"
+        } else if contiguity.contains("queen") || contiguity.contains("king") {
+            "King's"
+        } else {
+            "Rook's"
+        };
+        let summary = vec![
+            ("Input file".to_string(), input_file.clone()),
+            ("Number of cells included".to_string(), format!("{}", n)),
+            ("Contiguity type".to_string(), contiguity_name.to_string()),
+            ("Number of joins".to_string(), format!("{}", s0 / 2f64)),
+            (
+                "Join count threshold".to_string(),
+                format!("{} ({} black cells)", threshold, num_black),
+            ),
+            ("Number of permutations".to_string(), format!("{}", num_permutations)),
+        ];
+
+        if output_file.to_lowercase().ends_with(".json") {
+            let stats: Vec<serde_json::Value> = results.iter().map(|r| r.to_json()).collect();
+            let mut info = serde_json::Map::new();
+            for (key, value) in &summary {
+                info.insert(key.clone(), json!(value));
+            }
+            let report = json!({ "summary": info, "statistics": stats });
+            let f = File::create(output_file.clone())?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all(serde_json::to_string_pretty(&report)?.as_bytes())?;
+            let _ = writer.flush();
+            if verbose {
+                println!("Complete! Please see {} for output.", output_file);
+            }
+        } else {
+            write_html_report(&output_file, &summary, &results)?;
+            if verbose {
+                if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                    let output = Command::new("open")
+                        .arg(output_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "windows") {
+                    let output = Command::new("explorer.exe")
+                        .arg(output_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                } else if cfg!(target_os = "linux") {
+                    let output = Command::new("xdg-open")
+                        .arg(output_file.clone())
+                        .output()
+                        .expect("failed to execute process");
+
+                    let _ = output.stdout;
+                }
+                println!("Complete! Please see {} for output.", output_file);
+            }
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Sums the Moran's I cross-products, the Geary's C squared differences, the number of joins,
+/// and the black-black and black-white joins over all ordered pairs of neighbouring valid cells.
+fn neighbour_sums(
+    grid: &Array2D<f64>,
+    dx: &[isize],
+    dy: &[isize],
+    mean: f64,
+    threshold: f64,
+) -> [f64; 5] {
+    let nodata = grid.nodata();
+    let mut sums = [0f64; 5];
+    for row in 0..grid.rows() {
+        for col in 0..grid.columns() {
+            let z = grid.get_value(row, col);
+            if is_nodata(z, nodata) {
+                continue;
+            }
+            for i in 0..dx.len() {
+                let zn = grid.get_value(row + dy[i], col + dx[i]);
+                if !is_nodata(zn, nodata) {
+                    sums[0] += (z - mean) * (zn - mean);
+                    sums[1] += (z - zn) * (z - zn);
+                    sums[2] += 1f64;
+                    match (z >= threshold, zn >= threshold) {
+                        (true, true) => sums[3] += 1f64,
+                        (false, false) => {}
+                        _ => sums[4] += 1f64,
+                    }
+                }
+            }
+        }
+    }
+    sums
+}
+
+struct StatisticResult {
+    name: String,
+    observed: f64,
+    expected: f64,
+    permutation_mean: f64,
+    permutation_sd: f64,
+    z_score: f64,
+    p_value: f64,
+}
+
+impl StatisticResult {
+    fn new(name: &str, observed: f64, expected: f64, simulated: &[f64]) -> StatisticResult {
+        let r = simulated.len() as f64;
+        let (mut mean, mut sd, mut z, mut p) = (f64::NAN, f64::NAN, f64::NAN, f64::NAN);
+        if r > 1f64 {
+            mean = simulated.iter().sum::<f64>() / r;
+            sd = (simulated.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / (r - 1f64))
+                .sqrt();
+            if sd > 0f64 {
+                z = (observed - mean) / sd;
+            }
+            p = permutation_p_value(observed, simulated);
+        }
+        StatisticResult {
+            name: name.to_string(),
+            observed: observed,
+            expected: expected,
+            permutation_mean: mean,
+            permutation_sd: sd,
+            z_score: z,
+            p_value: p,
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let num = |v: f64| if v.is_finite() { json!(v) } else { serde_json::Value::Null };
+        json!({
+            "statistic": self.name,
+            "observed": num(self.observed),
+            "expected": num(self.expected),
+            "permutation_mean": num(self.permutation_mean),
+            "permutation_sd": num(self.permutation_sd),
+            "z_score": num(self.z_score),
+            "pseudo_p_value": num(self.p_value),
+        })
+    }
+}
+
+fn write_html_report(
+    output_file: &str,
+    summary: &[(String, String)],
+    results: &[StatisticResult],
+) -> Result<(), Error> {
+    let f = File::create(output_file)?;
+    let mut writer = BufWriter::new(f);
+
+    writer.write_all("<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+    <head>
+        <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+        <title>Global Spatial Autocorrelation</title>
+        <style  type=\"text/css\">
+            h1 {
+                font-size: 14pt;
+                margin-left: 15px;
+                margin-right: 15px;
+                text-align: center;
+                font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
+            }
+            p {
+                font-size: 12pt;
+                font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
+                margin-left: 15px;
+                margin-right: 15px;
+            }
+            caption {
+                font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
+                font-size: 12pt;
+                margin-left: 15px;
+                margin-right: 15px;
+            }
+            table {
+                font-size: 12pt;
+                font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
+                border-collapse: collapse;
+                align: center;
+            }
+            td, th {
+                border: 1px solid #222222;
+                text-align: centre;
+                padding: 8px;
+            }
+            tr:nth-child(even) {
+                background-color: #dddddd;
+            }
+            .numberCell {
+                text-align: right;
+            }
+        </style>
+    </head>
+    <body>
+        <h1>Global Spatial Autocorrelation Report</h1>
+    ".as_bytes())?;
+
+    for (key, value) in summary {
+        writer.write_all(&format!("<p><strong>{}</strong>: {}</p>", key, value).as_bytes())?;
+    }
+
+    writer.write_all("<div><table align=\"center\">".as_bytes())?;
+    writer.write_all("<caption>Global Statistics</caption>".as_bytes())?;
+    writer.write_all("<tr><th>Statistic</th><th>Observed</th><th>Expected</th><th>Permutation Mean</th><th>Permutation Std. Dev.</th><th>z-score</th><th>Pseudo p-value</th></tr>".as_bytes())?;
+    for r in results {
+        writer.write_all(
+            &format!(
+                "<tr><td>{}</td><td class=\"numberCell\">{:.5}</td><td class=\"numberCell\">{:.5}</td><td class=\"numberCell\">{:.5}</td><td class=\"numberCell\">{:.5}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td></tr>",
+                r.name, r.observed, r.expected, r.permutation_mean, r.permutation_sd, r.z_score, r.p_value
+            )
+            .as_bytes(),
+        )?;
+    }
+    writer.write_all("</table></div>".as_bytes())?;
+    writer.write_all("<p>The pseudo p-value is one-sided, in the direction of the deviation of the observed statistic from the permutation mean. Moran's I values greater than the expected value, and Geary's C values less than one, indicate positive spatial autocorrelation (clustering).</p>".as_bytes())?;
+    writer.write_all("</body>".as_bytes())?;
+
+    let _ = writer.flush();
+    Ok(())
+}
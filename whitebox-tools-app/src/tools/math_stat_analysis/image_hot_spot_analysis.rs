@@ -0,0 +1,533 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use self::statrs::distribution::{Normal, Univariate};
+use whitebox_common::algorithms::{fdr_critical_p_value, gi_star_z_score, local_morans_i_moments};
+use whitebox_common::structures::Array2D;
+use whitebox_raster::*;
+use crate::tools::*;
+use statrs;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool performs a local spatial autocorrelation (hot spot) analysis of a raster image. The
+/// neighbourhood of each grid cell is defined by a square focal window (`--filter`), within which all
+/// valid grid cells are given equal weight. Two local statistics are available (`--statistic`):
+///
+/// - **Getis-Ord Gi\*** (`gi_star`; the default) identifies statistically significant clusters of high
+///   values (hot spots) and low values (cold spots). The neighbourhood includes the centre cell.
+/// - **Local Moran's *I*** (`local_moran`; Anselin, 1995) identifies clusters of similar values (high-high
+///   and low-low) and spatial outliers (high-low and low-high). The neighbourhood excludes the centre
+///   cell, the weights are row-standardized, and the z-score is based on the expected value and variance
+///   of the statistic under the randomization assumption.
+///
+/// The main output (`--output`) is a raster of z-scores. Optionally, the user may also output a raster
+/// of two-sided p-values (`--pvalue_output`) and a categorical raster (`--category_output`) of
+/// significant clusters. For the Gi\* statistic, hot spots are coded 1, cold spots -1, and non-significant
+/// cells 0. For the Local Moran's *I*, the categories are 1 (high-high), 2 (low-low), 3 (high-low),
+/// 4 (low-high), and 0 (not significant). Significance is assessed at the level `--alpha`, optionally
+/// using the Benjamini-Hochberg false discovery rate (FDR) correction (`--fdr`), which is strongly
+/// recommended given the very large number of simultaneous tests involved in the analysis of an image.
+///
+/// Notice that the values of neighbouring grid cells are rarely independent and that the p-values
+/// should be interpreted with some caution, particularly for larger filter sizes.
+///
+/// # Reference
+/// Anselin, L. (1995). Local indicators of spatial association—LISA. Geographical Analysis, 27(2), 93-115.
+///
+/// Getis, A., & Ord, J. K. (1992). The analysis of spatial association by use of distance statistics.
+/// Geographical Analysis, 24(3), 189-206.
+///
+/// # See Also
+/// `HotSpotAnalysis`, `ImageAutocorrelation`, `ZScores`
+pub struct ImageHotSpotAnalysis {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ImageHotSpotAnalysis {
+    pub fn new() -> ImageHotSpotAnalysis {
+        // public constructor
+        let name = "ImageHotSpotAnalysis".to_string();
+        let toolbox = "Math and Stats Tools".to_string();
+        let description = "Performs a local spatial autocorrelation (Getis-Ord Gi* or Local Moran's I) analysis of a raster image.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input raster file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Z-Score File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output z-score raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Statistic".to_owned(),
+            flags: vec!["--statistic".to_owned()],
+            description: "Local statistic; options are 'gi_star' and 'local_moran'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec![
+                "gi_star".to_owned(),
+                "local_moran".to_owned(),
+            ]),
+            default_value: Some("gi_star".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Filter Size".to_owned(),
+            flags: vec!["--filter".to_owned()],
+            description: "Size of the focal window, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Significance Level (alpha)".to_owned(),
+            flags: vec!["--alpha".to_owned()],
+            description: "Significance level used to categorize grid cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.05".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Apply false discovery rate (FDR) correction?".to_owned(),
+            flags: vec!["--fdr".to_owned()],
+            description: "Apply the Benjamini-Hochberg false discovery rate correction.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output P-Value File (optional)".to_owned(),
+            flags: vec!["--pvalue_output".to_owned()],
+            description: "Optional output p-value raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Category File (optional)".to_owned(),
+            flags: vec!["--category_output".to_owned()],
+            description: "Optional output cluster/outlier category raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=image.tif -o=gi_star.tif --filter=5 --fdr --category_output=hot_spots.tif",
+            short_exe, name
+        ).replace("*", &sep);
+
+        ImageHotSpotAnalysis {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ImageHotSpotAnalysis {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut statistic = String::from("gi_star");
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(3usize);
+        let alpha = parsed.get_f64("alpha").unwrap_or(0.05f64);
+        let use_fdr = parsed.get_bool("fdr");
+        let mut pvalue_file = parsed.get_string("pvalue_output").unwrap_or_default();
+        let mut category_file = parsed.get_string("category_output").unwrap_or_default();
+
+        // read the arguments
+        if let Some(value) = parsed.get_string("statistic") {
+            statistic = value.to_lowercase();
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if filter_size < 3 {
+            filter_size = 3;
+        }
+        // The filter dimensions must be odd numbers such that there is a middle pixel
+        if (filter_size as f64 / 2f64).floor() == (filter_size as f64 / 2f64) {
+            filter_size += 1;
+        }
+        if alpha <= 0f64 || alpha >= 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The significance level (--alpha) must be between 0 and 1.",
+            ));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        pvalue_file = resolve_path(&pvalue_file, working_directory);
+        category_file = resolve_path(&category_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let is_moran = statistic.contains("moran");
+
+        // global moments of the image
+        let mut n = 0f64;
+        let mut sum = 0f64;
+        let mut sum_sqr = 0f64;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let z = input.get_value(row, col);
+                if !is_nodata(z, nodata) {
+                    n += 1f64;
+                    sum += z;
+                    sum_sqr += z * z;
+                }
+            }
+        }
+        if n < 3f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input image must contain at least three valid grid cells.",
+            ));
+        }
+        let mean = sum / n;
+        let m2 = (sum_sqr / n - mean * mean).max(0f64);
+        let std_dev = m2.sqrt();
+        let mut m4 = 0f64;
+        if is_moran {
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    let z = input.get_value(row, col);
+                    if !is_nodata(z, nodata) {
+                        m4 += (z - mean).powi(4);
+                    }
+                }
+            }
+            m4 /= n;
+        }
+        let b2 = if m2 > 0f64 { m4 / (m2 * m2) } else { 0f64 };
+
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let midpoint = (filter_size as f64 / 2f64).floor() as isize;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut z_data = vec![nodata; columns as usize];
+                    let mut i_data = vec![0f64; columns as usize];
+                    for col in 0..columns {
+                        let z = input.get_value(row, col);
+                        if is_nodata(z, nodata) {
+                            continue;
+                        }
+                        let mut count = 0f64;
+                        let mut neighbour_sum = 0f64;
+                        for r in row - midpoint..=row + midpoint {
+                            for c in col - midpoint..=col + midpoint {
+                                if r == row && c == col {
+                                    continue;
+                                }
+                                let zn = input.get_value(r, c);
+                                if !is_nodata(zn, nodata) {
+                                    count += 1f64;
+                                    neighbour_sum += zn;
+                                }
+                            }
+                        }
+                        if count == 0f64 {
+                            continue;
+                        }
+                        if is_moran {
+                            // row-standardized weights
+                            let lag = neighbour_sum / count - mean;
+                            let local_i = if m2 > 0f64 {
+                                (z - mean) / m2 * lag
+                            } else {
+                                0f64
+                            };
+                            let (expected, variance) =
+                                local_morans_i_moments(n, b2, 1f64, 1f64 / count);
+                            z_data[col as usize] = if variance > 0f64 {
+                                (local_i - expected) / variance.sqrt()
+                            } else {
+                                0f64
+                            };
+                            i_data[col as usize] = local_i;
+                        } else {
+                            z_data[col as usize] = gi_star_z_score(
+                                neighbour_sum + z,
+                                count + 1f64,
+                                count + 1f64,
+                                mean,
+                                std_dev,
+                                n,
+                            );
+                        }
+                    }
+                    if tx.send((row, z_data, i_data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        let mut local_i: Array2D<f64> = Array2D::new(rows, columns, 0f64, 0f64)?;
+        for r in 0..rows {
+            let (row, z_data, i_data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, z_data);
+            local_i.set_row_data(row, i_data);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // p-values and significance
+        let distribution = Normal::new(0.0, 1.0).unwrap();
+        let mut p_values: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut all_p_values = vec![];
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let z = output.get_value(row, col);
+                if !is_nodata(z, nodata) {
+                    let p = 2f64 * (1f64 - distribution.cdf(z.abs()));
+                    p_values.set_value(row, col, p);
+                    all_p_values.push(p);
+                }
+            }
+        }
+        let critical_p = if use_fdr {
+            fdr_critical_p_value(&all_p_values, alpha)
+        } else {
+            alpha
+        };
+        drop(all_p_values);
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            if use_fdr {
+                println!("FDR critical p-value: {:.6}", critical_p);
+            }
+            println!("Saving data...")
+        };
+
+        if !pvalue_file.is_empty() {
+            let mut output_p = Raster::initialize_using_file(&pvalue_file, &input);
+            output_p.configs.data_type = DataType::F32;
+            for row in 0..rows {
+                output_p.set_row_data(row, p_values.get_row_data(row));
+            }
+            output_p.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output_p.add_metadata_entry(format!("Input file: {}", input_file));
+            output_p.add_metadata_entry(format!("Statistic: {}", statistic));
+            output_p.add_metadata_entry(format!("Filter size: {}", filter_size));
+            let _ = match output_p.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("P-value file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if !category_file.is_empty() {
+            let mut output_cat = Raster::initialize_using_file(&category_file, &input);
+            output_cat.configs.data_type = DataType::I16;
+            output_cat.configs.nodata = -32768f64;
+            output_cat.configs.photometric_interp = PhotometricInterpretation::Categorical;
+            output_cat.reinitialize_values(-32768f64);
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    let p = p_values.get_value(row, col);
+                    if is_nodata(p, nodata) {
+                        continue;
+                    }
+                    let category = if p > critical_p {
+                        0f64
+                    } else if is_moran {
+                        let high = input.get_value(row, col) > mean;
+                        if local_i.get_value(row, col) > 0f64 {
+                            if high {
+                                1f64
+                            } else {
+                                2f64
+                            }
+                        } else if high {
+                            3f64
+                        } else {
+                            4f64
+                        }
+                    } else if output.get_value(row, col) > 0f64 {
+                        1f64
+                    } else {
+                        -1f64
+                    };
+                    output_cat.set_value(row, col, category);
+                }
+            }
+            output_cat.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output_cat.add_metadata_entry(format!("Input file: {}", input_file));
+            output_cat.add_metadata_entry(format!("Statistic: {}", statistic));
+            output_cat.add_metadata_entry(format!("Filter size: {}", filter_size));
+            output_cat.add_metadata_entry(format!("Alpha: {}", alpha));
+            output_cat.add_metadata_entry(format!("FDR correction: {}", use_fdr));
+            let _ = match output_cat.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Category file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Statistic: {}", statistic));
+        output.add_metadata_entry(format!("Filter size: {}", filter_size));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -10,7 +10,6 @@ use self::statrs::distribution::{FisherSnedecor, StudentsT, Univariate};
 use whitebox_raster::*;
 use whitebox_common::rendering::Scattergram;
 use crate::tools::*;
-use num_cpus;
 use rand::prelude::*;
 use statrs;
 use std::env;
@@ -18,13 +17,13 @@ use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::path::Path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a bivariate linear regression analysis on two input raster images. The first image
 /// (`--i1`) is considered to be the independent variable while the second image (`--i2`) is considered to
@@ -191,78 +190,21 @@ impl WhiteboxTool for ImageRegression {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1 = String::new();
-        let mut input_file2 = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut residuals_file = String::new();
-        let mut standardize_residuals = false;
+        let standardize_residuals = parsed.get_bool("standardize");
         let mut output_residuals = false;
-        let mut output_scattergram = false;
-        let mut num_samples = 1000usize;
+        let output_scattergram = parsed.get_bool("scattergram");
+        let num_samples = parsed.get_usize("num_samples")?.unwrap_or(1000usize);
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i1" || flag_val == "-input1" {
-                if keyval {
-                    input_file1 = vec[1].to_string();
-                } else {
-                    input_file1 = args[i + 1].to_string();
-                }
-            } else if flag_val == "-i2" || flag_val == "-input2" {
-                if keyval {
-                    input_file2 = vec[1].to_string();
-                } else {
-                    input_file2 = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-out_residuals" {
-                if keyval {
-                    residuals_file = vec[1].to_string();
-                } else {
-                    residuals_file = args[i + 1].to_string();
-                }
-                output_residuals = true;
-            } else if flag_val == "-standardize" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    standardize_residuals = true;
-                }
-            } else if flag_val == "-scattergram" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    output_scattergram = true;
-                }
-            } else if flag_val == "-num_samples" {
-                num_samples = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
+        if let Some(value) = parsed.get_string("out_residuals") {
+            residuals_file = value.to_string();
+            output_residuals = true;
         }
 
         if verbose {
@@ -276,18 +218,12 @@ impl WhiteboxTool for ImageRegression {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
 
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
 
         if output_file.len() == 0 {
             // output_file not specified and should be based on input file
@@ -300,9 +236,7 @@ impl WhiteboxTool for ImageRegression {
         if !output_file.ends_with(".html") {
             output_file = output_file + ".html";
         }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let input1 = Arc::new(Raster::new(&input_file1, "r")?);
         let rows = input1.configs.rows as isize;
@@ -321,18 +255,16 @@ impl WhiteboxTool for ImageRegression {
             println!("Loop 1 of 2...");
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let input1 = input1.clone();
             let input2 = input2.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut sum_x = 0f64;
                 let mut sum_y = 0f64;
                 let mut sum_xy = 0f64;
@@ -342,10 +274,13 @@ impl WhiteboxTool for ImageRegression {
                 let mut x: f64;
                 let mut y: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     for col in 0..columns {
                         x = input1.get_value(row, col);
                         y = input2.get_value(row, col);
-                        if x != nodata1 && y != nodata2 {
+                        if !is_nodata(x, nodata1) && !is_nodata(y, nodata2) {
                             sum_x += x;
                             sum_y += y;
                             sum_xy += x * y;
@@ -355,7 +290,9 @@ impl WhiteboxTool for ImageRegression {
                         }
                     }
                 }
-                tx.send((sum_x, sum_y, sum_xy, sum_xx, sum_yy, n)).unwrap();
+                if tx.send((sum_x, sum_y, sum_xy, sum_xx, sum_yy, n)).is_err() {
+                    return;
+                }
             });
         }
 
@@ -374,6 +311,7 @@ impl WhiteboxTool for ImageRegression {
             sum_yy += e;
             n += f;
         }
+        reporter.check_cancelled()?;
 
         let slope = (n * sum_xy - (sum_x * sum_y)) / (n * sum_xx - (sum_x * sum_x));
         let intercept = (sum_y - slope * sum_x) / n;
@@ -387,28 +325,35 @@ impl WhiteboxTool for ImageRegression {
             println!("Loop 2 of 2...");
         }
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let input1 = input1.clone();
             let input2 = input2.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut x: f64;
                 let mut y: f64;
                 let mut y_estimate: f64;
                 let mut ss_total = 0f64;
                 let mut ss_error = 0f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     for col in 0..columns {
                         x = input1[(row, col)];
                         y = input2[(row, col)];
-                        if x != nodata1 && y != nodata2 {
+                        if !is_nodata(x, nodata1) && !is_nodata(y, nodata2) {
                             y_estimate = slope * x + intercept;
                             ss_error += (y - y_estimate) * (y - y_estimate);
                             ss_total += (y - y_mean) * (y - y_mean);
                         }
                     }
                 }
-                tx.send((ss_error, ss_total)).unwrap();
+                if tx.send((ss_error, ss_total)).is_err() {
+                    return;
+                }
             });
         }
 
@@ -419,6 +364,7 @@ impl WhiteboxTool for ImageRegression {
             ss_error += a;
             ss_total += b;
         }
+        reporter.check_cancelled()?;
 
         let df_reg = 1f64;
         let df_error = n - 2f64;
@@ -444,15 +390,13 @@ impl WhiteboxTool for ImageRegression {
         let slope_pvalue = 2f64 * (1f64 - t.cdf(slope.abs() / slope_se));
 
         if output_residuals {
-            if !residuals_file.contains(&sep) {
-                residuals_file = format!("{}{}", working_directory, residuals_file);
-            }
+            residuals_file = resolve_path(&residuals_file, working_directory);
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let input1 = input1.clone();
                 let input2 = input2.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut x: f64;
                     let mut y: f64;
                     let mut y_estimate: f64;
@@ -462,7 +406,7 @@ impl WhiteboxTool for ImageRegression {
                         for col in 0..columns {
                             x = input1[(row, col)];
                             y = input2[(row, col)];
-                            if x != nodata1 && y != nodata2 {
+                            if !is_nodata(x, nodata1) && !is_nodata(y, nodata2) {
                                 y_estimate = slope * x + intercept;
                                 residual = if standardize_residuals {
                                     (y - y_estimate) / se_of_estimate
@@ -472,7 +416,9 @@ impl WhiteboxTool for ImageRegression {
                                 data[col as usize] = residual;
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -483,10 +429,11 @@ impl WhiteboxTool for ImageRegression {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 output.set_row_data(row, data);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Creating residuals image: {}%", progress);
+                        reporter.progress("Creating residuals image", progress);
                         old_progress = progress;
                     }
                 }
@@ -757,11 +704,12 @@ impl WhiteboxTool for ImageRegression {
             let mut sample_num = 0usize;
             let (mut x, mut y): (f64, f64);
             while sample_num < num_samples {
+                reporter.check_cancelled()?;
                 let row = rng.gen_range(0, rows as isize);
                 let col = rng.gen_range(0, columns as isize);
                 x = input1.get_value(row, col);
                 y = input2.get_value(row, col);
-                if x != nodata1 && y != nodata2 {
+                if !is_nodata(x, nodata1) && !is_nodata(y, nodata2) {
                     sample_num += 1;
                     series_xdata.push(x);
                     series_ydata.push(y);
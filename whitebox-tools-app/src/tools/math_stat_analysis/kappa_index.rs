@@ -2,19 +2,18 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 24/09/2017
-Last Modified: 12/10/2018
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_raster::*;
+use whitebox_common::rendering::{Report, ReportValue};
 use crate::tools::*;
 use std::cmp::max;
 use std::cmp::min;
 use std::env;
 use std::f64;
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::path::Path;
 use std::process::Command;
@@ -29,7 +28,8 @@ use std::process::Command;
 /// KIA is more robust than the *overall percent agreement* because it takes into account the agreement occurring by
 /// chance. A KIA of 0 would indicate that the classifier is no better than random class assignment. In addition to the
 /// KIA, this tool will also output the [producer's and user's accuracy](http://gis.humboldt.edu/OLM/Courses/GSP_216_Online/lesson6-2/metrics.html),
-/// the overall accuracy, and the error matrix.
+/// the overall accuracy, and the error matrix. When the `report_json` setting is enabled (e.g. `--report_json`),
+/// the tables and statistics of the report are also written to a JSON file of the same name.
 ///
 /// # See Also
 /// `CrossTabulation`
@@ -143,52 +143,12 @@ impl WhiteboxTool for KappaIndex {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1 = String::new();
-        let mut input_file2 = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i1"
-                || vec[0].to_lowercase() == "--i1"
-                || vec[0].to_lowercase() == "--input1"
-            {
-                if keyval {
-                    input_file1 = vec[1].to_string();
-                } else {
-                    input_file1 = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-i2"
-                || vec[0].to_lowercase() == "--i2"
-                || vec[0].to_lowercase() == "--input2"
-            {
-                if keyval {
-                    input_file2 = vec[1].to_string();
-                } else {
-                    input_file2 = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -201,20 +161,12 @@ impl WhiteboxTool for KappaIndex {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: i32;
         let mut old_progress: i32 = -1;
 
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
         if !output_file.ends_with(".html") {
             output_file = output_file + ".html";
         }
@@ -253,7 +205,7 @@ impl WhiteboxTool for KappaIndex {
             for col in 0..columns {
                 z1 = input1.get_value(row, col);
                 z2 = input2.get_value(row, col);
-                if z1 != nodata1 && z2 != nodata2 {
+                if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                     class1 = (z1 - min_val as f64).round() as usize;
                     class2 = (z2 - min_val as f64).round() as usize;
                     error_matrix[class1][class2] += 1;
@@ -262,297 +214,38 @@ impl WhiteboxTool for KappaIndex {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / rows as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
         }
 
-        let mut num_classes = 0;
-        for a in 0..range as usize {
-            if active_class[a] {
-                num_classes += 1;
-            }
-        }
-
-        let mut agreements = 0usize;
-        let mut expected_frequency = 0f64;
-        let mut n = 0usize;
-        let mut row_total: usize;
-        let mut col_total: usize;
-        let kappa: f64;
-        let overall_accuracy: f64;
-
-        for a in 0..range as usize {
-            agreements += error_matrix[a][a];
-            for b in 0..range as usize {
-                n += error_matrix[a][b];
-            }
-        }
-
-        for a in 0..range as usize {
-            row_total = 0;
-            col_total = 0;
-            for b in 0..range as usize {
-                col_total += error_matrix[a][b];
-                row_total += error_matrix[b][a];
-            }
-            expected_frequency += (col_total as f64 * row_total as f64) / (n as f64);
-        }
-
-        kappa = (agreements as f64 - expected_frequency as f64)
-            / (n as f64 - expected_frequency as f64);
-        overall_accuracy = agreements as f64 / n as f64;
-
-        let mut f = File::create(output_file.as_str()).unwrap();
-
-        let mut s = "<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
-        <head>
-            <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
-            <title>Lidar Kappa Index of Agreement</title>
-            <style  type=\"text/css\">
-                h1 {
-                    font-size: 14pt;
-                    margin-left: 15px;
-                    margin-right: 15px;
-                    text-align: center;
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                }
-                h3 {
-                    font-size: 12pt;
-                    margin-left: 15px;
-                    margin-right: 15px;
-                    text-align: left;
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                }
-                p, ol, ul, li {
-                    font-size: 12pt;
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                    margin-left: 15px;
-                    margin-right: 15px;
-                }
-                caption {
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                    font-size: 12pt;
-                    margin-left: 15px;
-                    margin-right: 15px;
-                }
-                table {
-                    font-size: 12pt;
-                    font-family: Helvetica, Verdana, Geneva, Arial, sans-serif;
-                    font-family: arial, sans-serif;
-                    border-collapse: collapse;
-                    align: center;
-                }
-                td {
-                    text-align: left;
-                    padding: 8px;
-                }
-                th {
-                    text-align: left;
-                    padding: 8px;
-                    background-color: #ffffff;
-                    border-bottom: 1px solid #333333;
-                    text-align: center;
-                }
-                tr:nth-child(1) {
-                    border-bottom: 1px solid #333333;
-                    border-top: 2px solid #333333;
-                }
-                tr:last-child {
-                    border-bottom: 2px solid #333333;
-                }
-                tr:nth-child(even) {
-                    background-color: #dddddd;
-                }
-                .numberCell {
-                    text-align: right;
-                }
-                .headerCell {
-                    text-align: center;
-                }
-            </style>
-        </head>
-        <body>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<body><h1>Kappa Index of Agreement</h1>";
-        f.write(s.as_bytes()).unwrap();
-        let path = Path::new(&input_file1);
-        let s1 = &format!(
-            "<p><strong>Classification Data:</strong> {}</p>",
-            path.file_name().unwrap().to_str().unwrap()
-        );
-        f.write_all(s1.as_bytes())?;
-        let path = Path::new(&input_file2);
-        let s1 = &format!(
-            "<p><strong>Reference Data:</strong> {}</p><br>",
-            path.file_name().unwrap().to_str().unwrap()
-        );
-        f.write_all(s1.as_bytes())?;
-        // let s2 = &format!("{}{}{}{}{}", "<p><b>Input Data:</b> <br><br><b>Classification Data:</b> ", input_file1, "<br><br><b>Reference Data:</b> ", input_file2, "<p>");
-        // f.write(s2.as_bytes()).unwrap();
-
-        s = "<br><table>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<caption>Contingency Table</caption>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<tr>";
-        f.write(s.as_bytes()).unwrap();
-        let s3 = &format!(
-            "{}{}{}",
-            "<th colspan=\"2\" rowspan=\"2\"></th><th colspan=\"",
-            num_classes,
-            "\">Reference Data</th><th rowspan=\"2\">Row<br>Totals</th>"
-        );
-        f.write(s3.as_bytes()).unwrap();
-        s = "</tr>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<tr>";
-        f.write(s.as_bytes()).unwrap();
-        for a in 0..range as usize {
-            if active_class[a] {
-                let s = &format!("{}{}{}", "<th>", (a as usize), "</th>");
-                f.write(s.as_bytes()).unwrap();
-            }
-        }
-
-        s = "</tr>";
-        f.write(s.as_bytes()).unwrap();
-        let mut first_entry = true;
-        for a in 0..range as usize {
-            if active_class[a] {
-                if first_entry {
-                    let s = format!(
-                        "{}{}{}{}{}",
-                        "<tr><td rowspan=\"",
-                        num_classes,
-                        "\" valign=\"center\"><b>Class<br>Data</b></td> <td><b>",
-                        (a as usize),
-                        "</b></td>"
-                    );
-                    f.write(s.as_bytes()).unwrap();
-                } else {
-                    let s = format!("{}{}{}", "<tr><td><b>", (a as usize), "</b></td>");
-                    f.write(s.as_bytes()).unwrap();
-                }
-                row_total = 0;
-                for b in 0..range as usize {
-                    if active_class[b] {
-                        row_total += error_matrix[a][b];
-                        let s = format!(
-                            "{}{}{}",
-                            "<td class=\"numberCell\">", error_matrix[a][b], "</td>"
-                        );
-                        f.write(s.as_bytes()).unwrap();
-                    }
-                }
-                let s = format!("{}{}{}", "<td class=\"numberCell\">", row_total, "</td>");
-                f.write(s.as_bytes()).unwrap();
-
-                let s2 = "</tr>";
-                f.write(s2.as_bytes()).unwrap();
-                first_entry = false;
-            }
-        }
-        s = "<tr>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<th colspan=\"2\">Column Totals</th>";
-        f.write(s.as_bytes()).unwrap();
-        for a in 0..range as usize {
-            if active_class[a] {
-                col_total = 0;
-                for b in 0..range as usize {
-                    if active_class[b] {
-                        col_total += error_matrix[b][a];
-                    }
-                }
-                let s = &format!("{}{}{}", "<td  class=\"numberCell\">", col_total, "</td>");
-                f.write(s.as_bytes()).unwrap();
-            }
-        }
-
-        let s4 = &format!(
-            "{}{}{}",
-            "<td class=\"numberCell\"><b>N</b>=", n, "</td></tr>"
-        );
-        f.write(s4.as_bytes()).unwrap();
-        s = "</table>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<br><br><table>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<caption>Class Statistics</caption>";
-        f.write(s.as_bytes()).unwrap();
-        s = "<tr><th class=\"headerCell\">Class</th><th class=\"headerCell\">User's Accuracy<sup>1</sup><br>(Reliability)</th><th class=\"headerCell\">Producer's Accuracy<sup>1</sup><br>(Accuracy)</th></tr>";
-        f.write(s.as_bytes()).unwrap();
-
-        let mut average_producers = 0.0;
-        let mut average_users = 0.0;
-        let mut num_active = 0.0;
-        for a in 0..range as usize {
-            if active_class[a] {
-                num_active += 1.0;
-                let mut row_total = 0;
-                let mut col_total = 0;
-                for b in 0..range as usize {
-                    if active_class[b] {
-                        col_total += error_matrix[a][b];
-                        row_total += error_matrix[b][a];
-                    }
-                }
-                average_users += 100.0 * error_matrix[a][a] as f64 / col_total as f64;
-                average_producers += 100.0 * error_matrix[a][a] as f64 / row_total as f64;
-                let s = &format!(
-                    "{}{}{}{}{}{}{}",
-                    "<tr><td>",
-                    (a as usize),
-                    "</td><td class=\"numberCell\">",
-                    format!(
-                        "{:.*}",
-                        2,
-                        (100.0 * error_matrix[a][a] as f64 / col_total as f64)
-                    ),
-                    "%</td><td class=\"numberCell\">",
-                    format!(
-                        "{:.*}",
-                        2,
-                        (100.0 * error_matrix[a][a] as f64 / row_total as f64)
-                    ),
-                    "%</td></tr>"
-                );
-                f.write(s.as_bytes()).unwrap();
-            }
-        }
-        f.write(format!("<tr><td>Average</td><td class=\"numberCell\">{}%</td><td class=\"numberCell\">{}%</td></tr>", format!("{:.*}", 2, average_users / num_active),
-                format!("{:.*}", 2, average_producers / num_active)).as_bytes()).unwrap();
-
-        s = "</table>";
-        f.write(s.as_bytes()).unwrap();
-        let s6 = &format!(
-            "<p>{}{}</p>",
-            "<p><b>Overall Accuracy</b> = ",
-            format!("{:.*}%", 2, overall_accuracy * 100.0)
-        );
-        f.write(s6.as_bytes()).unwrap();
-        let s7 = &format!(
-            "<p><b>Kappa</b><sup>2</sup> = {}</p>",
-            format!("{:.*}", 3, kappa)
-        );
-        f.write(s7.as_bytes()).unwrap();
-        let s5 = &format!("{}{}", "<p><br>Notes:<br>1. User's accuracy refers to the proportion of points correctly assigned to a class (i.e. the number of points correctly classified for a category divided by the row total in the contingency table) and is a measure of the reliability. ",
-                "Producer's accuracy is a measure of the proportion of the points in each category correctly classified (i.e. the number of points correctly classified for a category divided by the column total in the contingency table) and is a measure of the accuracy.<br>");
-        f.write(s5.as_bytes()).unwrap();
-        f.write("<br>2. Cohen's kappa coefficient is a statistic that measures inter-rater agreement for qualitative (categorical)
-        items. It is generally thought to be a more robust measure than simple percent agreement calculation, since
-        kappa takes into account the agreement occurring by chance. Kappa measures the percentage of data values in the
-        main diagonal of the contingency table and then adjusts these values for the amount of agreement that could be expected due
-        to chance alone.</p>".as_bytes()).unwrap();
-        s = "</body>";
-        f.write_all(s.as_bytes())?;
-
-        let _ = f.flush();
+        // the error matrix and labels of the classes that occur in either image
+        let classes: Vec<usize> = (0..range).filter(|&a| active_class[a]).collect();
+        let labels: Vec<String> = classes
+            .iter()
+            .map(|&a| (a as i32 + min_val).to_string())
+            .collect();
+        let matrix: Vec<Vec<usize>> = classes
+            .iter()
+            .map(|&a| classes.iter().map(|&b| error_matrix[a][b]).collect())
+            .collect();
+
+        let file_name = |f: &str| {
+            Path::new(f)
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        let mut report = Report::new("Kappa Index of Agreement");
+        report.add_property("Classification Data", file_name(&input_file1));
+        report.add_property("Reference Data", file_name(&input_file2));
+        add_kappa_analysis(&mut report, &labels, &matrix);
+        report.write(&output_file)?;
 
         if verbose {
             if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
@@ -593,3 +286,76 @@ impl WhiteboxTool for KappaIndex {
         Ok(())
     }
 }
+
+/// Adds the contingency table, class statistics, overall accuracy, and kappa index of an
+/// accuracy assessment to a report. `matrix[a][b]` is the number of samples of class `a` in
+/// the classification data and class `b` in the reference data, and `labels` names the classes.
+pub(crate) fn add_kappa_analysis(report: &mut Report, labels: &[String], matrix: &[Vec<usize>]) {
+    let num_classes = labels.len();
+    let row_totals: Vec<usize> = (0..num_classes)
+        .map(|a| matrix[a].iter().sum())
+        .collect();
+    let col_totals: Vec<usize> = (0..num_classes)
+        .map(|b| (0..num_classes).map(|a| matrix[a][b]).sum())
+        .collect();
+    let n: usize = row_totals.iter().sum();
+    let agreements: usize = (0..num_classes).map(|a| matrix[a][a]).sum();
+    let expected_frequency: f64 = (0..num_classes)
+        .map(|a| row_totals[a] as f64 * col_totals[a] as f64 / n as f64)
+        .sum();
+    let kappa = (agreements as f64 - expected_frequency) / (n as f64 - expected_frequency);
+    let overall_accuracy = agreements as f64 / n as f64;
+
+    let mut columns = vec!["Class"];
+    columns.extend(labels.iter().map(|l| l.as_str()));
+    columns.push("Row Totals");
+    let mut rows = vec![];
+    for a in 0..num_classes {
+        let mut row = vec![ReportValue::from(labels[a].as_str())];
+        row.extend(matrix[a].iter().map(|&v| ReportValue::from(v)));
+        row.push(row_totals[a].into());
+        rows.push(row);
+    }
+    let mut row = vec![ReportValue::from("Column Totals")];
+    row.extend(col_totals.iter().map(|&v| ReportValue::from(v)));
+    row.push(n.into());
+    rows.push(row);
+    report.add_table(
+        "Contingency Table (rows: classification data; columns: reference data)",
+        &columns,
+        rows,
+    );
+
+    let mut rows = vec![];
+    let mut average_users = 0.0;
+    let mut average_producers = 0.0;
+    for a in 0..num_classes {
+        let users = 100.0 * matrix[a][a] as f64 / row_totals[a] as f64;
+        let producers = 100.0 * matrix[a][a] as f64 / col_totals[a] as f64;
+        average_users += users;
+        average_producers += producers;
+        rows.push(vec![
+            labels[a].as_str().into(),
+            ReportValue::Real(users, 2),
+            ReportValue::Real(producers, 2),
+        ]);
+    }
+    rows.push(vec![
+        "Average".into(),
+        ReportValue::Real(average_users / num_classes as f64, 2),
+        ReportValue::Real(average_producers / num_classes as f64, 2),
+    ]);
+    report.add_table(
+        "Class Statistics",
+        &["Class", "User's Accuracy (%)", "Producer's Accuracy (%)"],
+        rows,
+    );
+
+    report.add_property("Overall Accuracy (%)", ReportValue::Real(overall_accuracy * 100.0, 2));
+    report.add_property("Kappa", ReportValue::Real(kappa, 3));
+    report.add_paragraph("Notes:<br>1. User's accuracy refers to the proportion of points correctly assigned to a class (i.e. the number of points correctly classified for a category divided by the row total in the contingency table) and is a measure of the reliability. \
+        Producer's accuracy is a measure of the proportion of the points in each category correctly classified (i.e. the number of points correctly classified for a category divided by the column total in the contingency table) and is a measure of the accuracy.<br>\
+        2. Cohen's kappa coefficient is a statistic that measures inter-rater agreement for qualitative (categorical) items. It is generally thought to be a more robust measure than simple percent agreement calculation, since \
+        kappa takes into account the agreement occurring by chance. Kappa measures the percentage of data values in the main diagonal of the contingency table and then adjusts these values for the amount of agreement that could be expected due \
+        to chance alone.");
+}
@@ -16,7 +16,7 @@ use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::process::Command;
 
@@ -50,7 +50,7 @@ impl KsTestForNormality {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input File".to_owned(),
-            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            flags: vec!["--base".to_owned(), "-i".to_owned(), "--input".to_owned()],
             description: "Input raster file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -141,53 +141,12 @@ impl WhiteboxTool for KsTestForNormality {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut num_samples = 0usize;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-base" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-num_samples" {
-                num_samples = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut num_samples = parsed.get_usize("num_samples")?.unwrap_or(0usize);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -200,14 +159,8 @@ impl WhiteboxTool for KsTestForNormality {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
         if !output_file.ends_with(".html") {
             output_file = output_file + ".html";
         }
@@ -248,7 +201,7 @@ impl WhiteboxTool for KsTestForNormality {
             for row in 0..rows {
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         bin_num = ((z - min_value) / bin_size).floor() as usize;
                         if bin_num > num_bins_less_one {
                             bin_num = num_bins_less_one;
@@ -261,10 +214,11 @@ impl WhiteboxTool for KsTestForNormality {
                         freq_data[bin_num] += 1;
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / rows as f64) as i32;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -275,14 +229,15 @@ impl WhiteboxTool for KsTestForNormality {
             for row in 0..rows {
                 for col in 0..columns {
                     z = input.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         total_deviation += (z - mean) * (z - mean);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / rows as f64) as i32;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -300,7 +255,7 @@ impl WhiteboxTool for KsTestForNormality {
                 row = rng.gen_range(0, rows as isize); // row_rng.ind_sample(&mut rng);
                 col = rng.gen_range(0, columns as isize); // col_rng.ind_sample(&mut rng);
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     bin_num = ((z - min_value) / bin_size).floor() as usize;
                     if bin_num > num_bins_less_one {
                         bin_num = num_bins_less_one;
@@ -316,10 +271,11 @@ impl WhiteboxTool for KsTestForNormality {
                     cell_index = row * columns + col;
                     sample_cells.push(cell_index);
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * sample_num as f64 / num_samples as f64) as i32;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress as usize);
                             old_progress = progress;
                         }
                     }
@@ -335,10 +291,11 @@ impl WhiteboxTool for KsTestForNormality {
                 z = input.get_value(row, col);
                 total_deviation += (z - mean) * (z - mean);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * sample_num as f64 / num_samples as f64) as i32;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress as usize);
                         old_progress = progress;
                     }
                 }
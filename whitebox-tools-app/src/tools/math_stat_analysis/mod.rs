@@ -26,10 +26,14 @@ mod equal_to;
 mod exp;
 mod exp2;
 mod floor;
+mod global_autocorrelation;
 mod greater_than;
+mod hot_spot_analysis;
 mod image_autocorrelation;
 mod image_correlation;
 mod image_correlation_neighbourhood_analysis;
+mod image_global_autocorrelation;
+mod image_hot_spot_analysis;
 mod image_regression;
 mod increment;
 mod inplace_add;
@@ -112,10 +116,14 @@ pub use self::equal_to::EqualTo;
 pub use self::exp::Exp;
 pub use self::exp2::Exp2;
 pub use self::floor::Floor;
+pub use self::global_autocorrelation::GlobalAutocorrelation;
 pub use self::greater_than::GreaterThan;
+pub use self::hot_spot_analysis::HotSpotAnalysis;
 pub use self::image_autocorrelation::ImageAutocorrelation;
 pub use self::image_correlation::ImageCorrelation;
 pub use self::image_correlation_neighbourhood_analysis::ImageCorrelationNeighbourhoodAnalysis;
+pub use self::image_global_autocorrelation::ImageGlobalAutocorrelation;
+pub use self::image_hot_spot_analysis::ImageHotSpotAnalysis;
 pub use self::image_regression::ImageRegression;
 pub use self::increment::Increment;
 pub use self::inplace_add::InPlaceAdd;
@@ -125,6 +133,7 @@ pub use self::inplace_subtract::InPlaceSubtract;
 pub use self::integer_division::IntegerDivision;
 pub use self::isnodata::IsNoData;
 pub use self::kappa_index::KappaIndex;
+pub(crate) use self::kappa_index::add_kappa_analysis;
 pub use self::ks_normality_test::KsTestForNormality;
 pub use self::less_than::LessThan;
 pub use self::list_unique_values::ListUniqueValues;
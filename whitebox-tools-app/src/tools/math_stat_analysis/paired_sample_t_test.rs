@@ -17,12 +17,12 @@ use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool will perform a paired-sample *t*-test to evaluate whether a significant
 /// statistical difference exists between the two rasters. The null hypothesis is that
@@ -161,60 +161,13 @@ impl WhiteboxTool for PairedSampleTTest {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1 = String::new();
-        let mut input_file2 = String::new();
-        let mut output_file = String::new();
-        let mut num_samples = 0usize;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-input1" {
-                input_file1 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-input2" {
-                input_file2 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-num_samples" {
-                num_samples = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut num_samples = parsed.get_usize("num_samples")?.unwrap_or(0usize);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -227,17 +180,9 @@ impl WhiteboxTool for PairedSampleTTest {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
         if !output_file.ends_with(".html") {
             output_file = output_file + ".html";
         }
@@ -250,11 +195,9 @@ impl WhiteboxTool for PairedSampleTTest {
         if input1.configs.rows != input2.configs.rows
             || input1.configs.columns != input2.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input1, &input2, verbose);
 
         let start = Instant::now();
         let mut progress: i32;
@@ -280,18 +223,14 @@ impl WhiteboxTool for PairedSampleTTest {
         let mut diffs: Vec<f64> = Vec::with_capacity((rows * columns) as usize);
 
         if num_samples == 0 {
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let input1 = input1.clone();
                 let input2 = input2.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z1: f64;
                     let mut z2: f64;
                     let mut diff: f64;
@@ -303,7 +242,7 @@ impl WhiteboxTool for PairedSampleTTest {
                         for col in 0..columns {
                             z1 = input1.get_value(row, col);
                             z2 = input2.get_value(row, col);
-                            if z1 != nodata1 && z2 != nodata2 {
+                            if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                                 n += 1;
                                 diff = z2 - z1;
                                 s += diff;
@@ -311,7 +250,9 @@ impl WhiteboxTool for PairedSampleTTest {
                                 diffs.push(diff);
                             }
                         }
-                        tx.send((n, s, sq, diffs)).unwrap();
+                        if tx.send((n, s, sq, diffs)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -326,10 +267,11 @@ impl WhiteboxTool for PairedSampleTTest {
                 for i in 0..d.len() {
                     diffs.push(d[i]);
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -354,7 +296,7 @@ impl WhiteboxTool for PairedSampleTTest {
                 col = rng.gen_range(0, columns as isize);
                 z1 = input1.get_value(row, col);
                 z2 = input2.get_value(row, col);
-                if z1 != nodata1 && z2 != nodata2 {
+                if !is_nodata(z1, nodata1) && !is_nodata(z2, nodata2) {
                     diff = z2 - z1;
                     n += 1;
                     sum += diff;
@@ -364,10 +306,11 @@ impl WhiteboxTool for PairedSampleTTest {
                     data2.push(z2);
                     diffs.push(z2 - z1);
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * sample_num as f64 / num_samples as f64) as i32;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress as usize);
                             old_progress = progress;
                         }
                     }
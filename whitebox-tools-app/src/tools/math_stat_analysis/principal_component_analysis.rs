@@ -180,59 +180,18 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files_str = String::new();
-        let mut output_html_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files_str = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_html_file = parsed.get_string("output").unwrap_or_default();
         let mut num_comp = 0usize;
         let mut num_comp_set = false;
-        let mut standardized = false;
+        let standardized = parsed.get_bool("standardized");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                input_files_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_html" || flag_val == "-output" {
-                output_html_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-num_comp" {
-                num_comp = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-                num_comp_set = true;
-            } else if flag_val == "-standardized" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    standardized = true;
-                }
-            }
+        if let Some(value) = parsed.get_f64("num_comp") {
+            num_comp = value as usize;
+            num_comp_set = true;
         }
 
         if verbose {
@@ -276,9 +235,7 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
             working_directory.clone().to_owned()
         };
 
-        if !output_html_file.contains(&sep) && !output_html_file.contains("/") && !output_html_file.is_empty() {
-            output_html_file = format!("{}{}", wd, output_html_file);
-        }
+        output_html_file = resolve_path(&output_html_file, &wd);
 
         if !output_html_file.ends_with(".html") && !output_html_file.is_empty() {
             output_html_file.push_str(".html");
@@ -301,9 +258,7 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
             if !input_files[i].trim().is_empty() {
                 // quality control on the image file name.
                 let mut input_file = input_files[i].trim().to_owned();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
 
                 // read the image
                 // let input = Raster::new(&input_file, "r")?;
@@ -323,8 +278,7 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
                     if input_raster[i].configs.rows as isize != rows
                         || input_raster[i].configs.columns as isize != columns
                     {
-                        return Err(Error::new(ErrorKind::InvalidInput,
-                            "All input images must share the same dimensions (rows and columns) and spatial extent."));
+                        return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("All input images must share the same dimensions (rows and columns) and spatial extent."));
                     }
                 }
             } else {
@@ -351,22 +305,23 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
             for col in 0..columns {
                 for i in 0..num_files {
                     z1 = input_raster[i].get_value(row, col);
-                    if z1 != nodata[i] {
+                    if !is_nodata(z1, nodata[i]) {
                         image_total_deviations[i] += (z1 - average[i]) * (z1 - average[i]);
 
                         for a in 0..num_files {
                             z2 = input_raster[a].get_value(row, col);
-                            if z2 != nodata[a] {
+                            if !is_nodata(z2, nodata[a]) {
                                 covariances[i][a] += (z1 - average[i]) * (z2 - average[a]);
                             }
                         }
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -555,10 +510,6 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
             &format!("<div id='graph' align=\"center\">{}</div>", graph.get_svg()).as_bytes(),
         )?;
 
-
-
-
-
         // The following will print a full-precision version of the eigenvectors as a comment (hidden from the user).
         // It is intended to be parsed by the InversePCA tool to perform the inverse operation.
 
@@ -579,9 +530,6 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
 
         writer.write_all("\n-->".as_bytes())?;
 
-
-
-
         writer.write_all("\n</body>".as_bytes())?;
 
         let _ = writer.flush();
@@ -627,7 +575,7 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
             for row in 0..rows {
                 for col in 0..columns {
                     z1 = input_raster[0].get_value(row, col);
-                    if z1 != nodata[0] {
+                    if !is_nodata(z1, nodata[0]) {
                         z1 = 0f64;
                         for k in 0..num_files {
                             z1 += input_raster[k].get_value(row, col)
@@ -636,10 +584,11 @@ impl WhiteboxTool for PrincipalComponentAnalysis {
                         output.set_value(row, col, z1);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Saving component image {}: {}%", (a + 1), progress);
+                        reporter.progress(&format!("Saving component image {}", (a + 1)), progress);
                         old_progress = progress;
                     }
                 }
@@ -2,26 +2,24 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 19/12/2017
-Last Modified: 24/01/2019
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_raster::*;
-use whitebox_common::rendering::html::*;
-use whitebox_common::rendering::Histogram;
+use whitebox_common::rendering::{Histogram, Report};
 use crate::tools::*;
 use std::env;
 use std::f64;
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::process::Command;
 
 /// This tool produces a histogram (i.e. a frequency distribution graph) for the values contained within
 /// an input raster file (`--input`). The histogram will be embedded within an output (`--output`)
-/// HTML file, which should be automatically displayed after the tool has completed.
+/// HTML file, which should be automatically displayed after the tool has completed. When the
+/// `report_json` setting is enabled (e.g. `--report_json`), the bin frequencies are also written to
+/// a JSON file of the same name.
 ///
 /// # See Also
 /// `AttributeHistogram`
@@ -130,40 +128,11 @@ impl WhiteboxTool for RasterHistogram {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -176,19 +145,13 @@ impl WhiteboxTool for RasterHistogram {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
         let start = Instant::now();
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Raster::new(&input_file, "r")?;
         let rows = input.configs.rows as isize;
@@ -211,15 +174,16 @@ impl WhiteboxTool for RasterHistogram {
         for row in 0..rows {
             for col in 0..columns {
                 val = input.get_value(row, col);
-                if val != nodata && val >= min && val <= max {
+                if !is_nodata(val, nodata) && val >= min && val <= max {
                     bin = ((val - min) / bin_width).floor() as usize;
                     freq_data[bin] += 1;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Binning the data: {}%", progress);
+                    reporter.progress("Binning the data", progress);
                     old_progress = progress;
                 }
             }
@@ -234,29 +198,9 @@ impl WhiteboxTool for RasterHistogram {
             );
         }
 
-        let f = File::create(output_file.clone())?;
-        let mut writer = BufWriter::new(f);
-
-        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
-        <head>
-            <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
-            <title>Histogram Analysis</title>"#.as_bytes())?;
-
-        // get the style sheet
-        writer.write_all(&get_css().as_bytes())?;
-
-        writer.write_all(
-            &r#"</head>
-        <body>
-            <h1>Histogram Analysis</h1>"#
-                .as_bytes(),
-        )?;
-
-        writer.write_all(
-            &format!("<p><strong>Image</strong>: {}</p>", input_file.clone()).as_bytes(),
-        )?;
-
-        let histo = Histogram {
+        let mut report = Report::new("Histogram Analysis");
+        report.add_property("Image", input_file.clone());
+        report.add_histogram(Histogram {
             parent_id: "histo".to_owned(),
             width: 700f64,
             height: 500f64,
@@ -265,15 +209,8 @@ impl WhiteboxTool for RasterHistogram {
             bin_width: bin_width,
             x_axis_label: "Image Value (X)".to_owned(),
             cumulative: false,
-        };
-
-        writer.write_all(
-            &format!("<div id='histo' align=\"center\">{}</div>", histo.get_svg()).as_bytes(),
-        )?;
-
-        writer.write_all("</body>".as_bytes())?;
-
-        let _ = writer.flush();
+        });
+        report.write(&output_file)?;
 
         if verbose {
             if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
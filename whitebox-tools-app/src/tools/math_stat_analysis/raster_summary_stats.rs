@@ -2,24 +2,22 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 01/07/2017
-Last Modified: 13/10/2018
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
-use std::sync::mpsc;
-use std::sync::Arc;
-use std::thread;
 
 /// This tool outputs distribution summary statistics for input raster images (`--input`).
 /// The distribution statistics include the raster minimum, maximum, range, total, mean,
 /// variance, and standard deviation. These summary statistics are output to the system `stdout`.
+/// When the input raster has an up-to-date `.aux.xml` statistics sidecar file, which WhiteboxTools
+/// writes alongside output rasters, the statistics are read from the sidecar rather than calculated.
 ///
 /// The following is an example of the summary report:
 ///
@@ -136,32 +134,10 @@ impl WhiteboxTool for RasterSummaryStats {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        _reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -174,94 +150,25 @@ impl WhiteboxTool for RasterSummaryStats {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        let mut progress: usize;
-        let mut old_progress: usize = 1;
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
         if verbose {
             println!("Reading data...")
         };
 
-        let input = Arc::new(Raster::new(&input_file, "r")?);
+        let input = Raster::new(&input_file, "r")?;
 
         let start = Instant::now();
-        let rows = input.configs.rows as isize;
-        let columns = input.configs.columns as isize;
-        let nodata = input.configs.nodata;
-
-        //if verbose { println!("Calculating image mean and standard deviation...") };
-        //let (mean, stdev) = input.calculate_mean_and_stdev();
-
-        let mut num_procs = num_cpus::get() as isize;
-        let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-        let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let tx = tx.clone();
-            thread::spawn(move || {
-                let mut z: f64;
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    let mut n = 0;
-                    let mut s = 0.0;
-                    let mut sq = 0.0;
-                    let mut minz = f64::INFINITY;
-                    let mut maxz = f64::NEG_INFINITY;
-                    for col in 0..columns {
-                        z = input[(row, col)];
-                        if z != nodata {
-                            n += 1;
-                            s += z;
-                            sq += z * z;
-                            if z < minz {
-                                minz = z;
-                            }
-                            if z > maxz {
-                                maxz = z;
-                            }
-                        }
-                    }
-                    tx.send((n, s, sq, minz, maxz)).unwrap();
-                }
-            });
-        }
-
-        let mut num_cells = 0;
-        let mut sum = 0.0;
-        let mut sq_sum = 0.0;
-        let mut minz = f64::INFINITY;
-        let mut maxz = f64::NEG_INFINITY;
-        for row in 0..rows {
-            let (a, b, c, d, e) = rx.recv().expect("Error receiving data from thread.");
-            num_cells += a;
-            sum += b;
-            sq_sum += c;
-            if d < minz {
-                minz = d;
-            }
-            if e > maxz {
-                maxz = e;
-            }
-            if verbose {
-                progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
-                if progress != old_progress {
-                    println!("Progress: {}%", progress);
-                    old_progress = progress;
-                }
-            }
-        }
 
-        let mean = sum / num_cells as f64;
-        let variance = sq_sum / num_cells as f64 - mean * mean;
-        let std_dev = variance.sqrt();
+        // The statistics are read from the raster's .aux.xml sidecar when it is up to date.
+        let stats = input.get_statistics();
+        let num_cells = stats.num_valid_cells;
+        let minz = stats.minimum;
+        let maxz = stats.maximum;
+        let mean = stats.mean;
+        let sum = mean * num_cells as f64;
+        let std_dev = stats.std_dev;
+        let variance = std_dev * std_dev;
 
         let elapsed_time = get_formatted_elapsed_time(start);
 
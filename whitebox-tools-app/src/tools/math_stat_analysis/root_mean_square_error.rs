@@ -8,15 +8,14 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::cmp::Ordering::Equal;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool calculates the root-mean-square-error (RMSE) or root-mean-square-difference (RMSD) from two
 /// input rasters. If the two input rasters possess the same number of rows and columns, the RMSE is
@@ -77,7 +76,7 @@ impl RootMeanSquareError {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=DEM.tif",
+            ">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -i=DEM.tif --base=base.tif",
             short_exe, name
         )
         .replace("*", &sep);
@@ -132,39 +131,11 @@ impl WhiteboxTool for RootMeanSquareError {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut base_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i" || vec[0].to_lowercase() == "--input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-base" || vec[0].to_lowercase() == "--base" {
-                if keyval {
-                    base_file = vec[1].to_string();
-                } else {
-                    base_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -177,17 +148,11 @@ impl WhiteboxTool for RootMeanSquareError {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !base_file.contains(&sep) && !base_file.contains("/") {
-            base_file = format!("{}{}", working_directory, base_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        base_file = resolve_input_path(&base_file, working_directory)?;
 
         if verbose {
             println!("Reading data...")
@@ -210,18 +175,14 @@ impl WhiteboxTool for RootMeanSquareError {
             && base_raster.configs.columns as isize == columns
         {
             // The two grids are the same resolution. This simplifies the calculation greatly.
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let input = input.clone();
                 let base_raster = base_raster.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z1: f64;
                     let mut z2: f64;
                     let mut z_diff: f64;
@@ -233,7 +194,7 @@ impl WhiteboxTool for RootMeanSquareError {
                         for col in 0..columns {
                             z1 = input[(row, col)];
                             z2 = base_raster[(row, col)];
-                            if z1 != nodata && z2 != nodata_base {
+                            if !is_nodata(z1, nodata) && !is_nodata(z2, nodata_base) {
                                 z_diff = z2 - z1;
                                 n += 1;
                                 s += z_diff;
@@ -241,7 +202,9 @@ impl WhiteboxTool for RootMeanSquareError {
                                 data.push(z_diff.abs() as f32);
                             }
                         }
-                        tx.send((n, s, sq, data)).unwrap();
+                        if tx.send((n, s, sq, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -260,10 +223,11 @@ impl WhiteboxTool for RootMeanSquareError {
                     abs_residuals.push(d[i]);
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -291,18 +255,14 @@ impl WhiteboxTool for RootMeanSquareError {
         } else {
             /* The two grids are not of the same resolution. Bilinear resampling will have to be
             carried out to estimate z-values. Base image = source; input image = destination */
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let input = input.clone();
                 let base_raster = base_raster.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut y: f64;
                     let mut x: f64;
                     let mut z1: f64;
@@ -331,7 +291,7 @@ impl WhiteboxTool for RootMeanSquareError {
                         let mut data: Vec<f32> = Vec::with_capacity(columns as usize);
                         for col in 0..columns {
                             z1 = input[(row, col)];
-                            if z1 != nodata {
+                            if !is_nodata(z1, nodata) {
                                 x = input.get_x_from_column(col);
                                 src_row = (src_north - y) / src_resolution_y;
                                 src_col = (x - src_west) / src_resolution_x;
@@ -345,10 +305,10 @@ impl WhiteboxTool for RootMeanSquareError {
                                 n2 = base_raster[(origin_row + 1, origin_col)];
                                 n3 = base_raster[(origin_row + 1, origin_col + 1)];
 
-                                if n0 != nodata_base
-                                    && n1 != nodata_base
-                                    && n2 != nodata_base
-                                    && n3 != nodata_base
+                                if !is_nodata(n0, nodata_base)
+                                    && !is_nodata(n1, nodata_base)
+                                    && !is_nodata(n2, nodata_base)
+                                    && !is_nodata(n3, nodata_base)
                                 {
                                     // This is the bilinear interpolation equation.
                                     z2 = n0 * (1f64 - dx) * (1f64 - dy)
@@ -357,22 +317,22 @@ impl WhiteboxTool for RootMeanSquareError {
                                         + n3 * dx * dy;
                                 } else {
                                     // some of the neighbours are nodata and an inverse-distance scheme is used instead
-                                    let w0 = if n0 != nodata_base {
+                                    let w0 = if !is_nodata(n0, nodata_base) {
                                         1f64 / (dx * dx + dy * dy)
                                     } else {
                                         0f64
                                     };
-                                    let w1 = if n1 != nodata_base {
+                                    let w1 = if !is_nodata(n1, nodata_base) {
                                         1f64 / ((1f64 - dx) * (1f64 - dx) + dy * dy)
                                     } else {
                                         0f64
                                     };
-                                    let w2 = if n2 != nodata_base {
+                                    let w2 = if !is_nodata(n2, nodata_base) {
                                         1f64 / (dx * dx + (1f64 - dy) * (1f64 - dy))
                                     } else {
                                         0f64
                                     };
-                                    let w3 = if n3 != nodata_base {
+                                    let w3 = if !is_nodata(n3, nodata_base) {
                                         1f64 / ((1f64 - dx) * (1f64 - dx)
                                             + (1f64 - dy) * (1f64 - dy))
                                     } else {
@@ -386,7 +346,7 @@ impl WhiteboxTool for RootMeanSquareError {
                                     }
                                 }
 
-                                if z2 != nodata_base && !z2.is_nan() {
+                                if !is_nodata(z2, nodata_base) && !z2.is_nan() {
                                     z_diff = z2 - z1;
                                     n += 1;
                                     s += z_diff;
@@ -395,7 +355,9 @@ impl WhiteboxTool for RootMeanSquareError {
                                 }
                             }
                         }
-                        tx.send((n, s, sq, data)).unwrap();
+                        if tx.send((n, s, sq, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -414,10 +376,11 @@ impl WhiteboxTool for RootMeanSquareError {
                     abs_residuals.push(d[i]);
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
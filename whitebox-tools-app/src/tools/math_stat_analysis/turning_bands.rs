@@ -8,18 +8,17 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use rand::prelude::*;
 // use rand::{Rng, SeedableRng};
 use rand::thread_rng;
 use rand_distr::StandardNormal;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to create a random field using the turning bands algorithm. The user must specify
 /// the name of a base raster image (`--base`) from which the output raster will derive its geographical
@@ -65,7 +64,7 @@ impl TurningBandsSimulation {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input Base File".to_owned(),
-            flags: vec!["-i".to_owned(), "--base".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--base".to_owned()],
             description: "Input base raster file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -166,68 +165,13 @@ impl WhiteboxTool for TurningBandsSimulation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut range = 1f64;
-        let mut iterations = 1000;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-base" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-range" {
-                if keyval {
-                    range = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    range = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-iterations" {
-                if keyval {
-                    iterations = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    iterations = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("base").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let range = parsed.get_f64("range").unwrap_or(1f64);
+        let iterations = parsed.get_usize("iterations")?.unwrap_or(1000);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -240,14 +184,8 @@ impl WhiteboxTool for TurningBandsSimulation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Raster::new(&input_file, "r")?;
 
@@ -442,18 +380,14 @@ impl WhiteboxTool for TurningBandsSimulation {
             }
 
             // scan through each grid cell and assign it the closest value on the line segment
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             let y = Arc::new(y);
             for tid in 0..num_procs {
                 let y = y.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
                         let mut perpendicular_line_intercept: f64;
                         let (mut intersecting_point_x, mut intersecting_point_y): (f64, f64);
@@ -478,7 +412,9 @@ impl WhiteboxTool for TurningBandsSimulation {
                             }
                             data[col as usize] = y[p as usize];
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -488,10 +424,11 @@ impl WhiteboxTool for TurningBandsSimulation {
                 output.increment_row_data(row, data);
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * i as f64 / (iterations - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 2)", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -504,10 +441,11 @@ impl WhiteboxTool for TurningBandsSimulation {
                 output[(row, col)] = output[(row, col)] / iterations_rooted;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 2)", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -17,7 +17,7 @@ use std::f64;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::BufWriter;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::process::Command;
 
@@ -157,60 +157,13 @@ impl WhiteboxTool for TwoSampleKsTest {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file1 = String::new();
-        let mut input_file2 = String::new();
-        let mut output_file = String::new();
-        let mut num_samples = 0usize;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-input1" {
-                input_file1 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-input2" {
-                input_file2 = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-num_samples" {
-                num_samples = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file1 = parsed.get_string("input1").unwrap_or_default();
+        let mut input_file2 = parsed.get_string("input2").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut num_samples = parsed.get_usize("num_samples")?.unwrap_or(0usize);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -223,17 +176,9 @@ impl WhiteboxTool for TwoSampleKsTest {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file1.contains(&sep) && !input_file1.contains("/") {
-            input_file1 = format!("{}{}", working_directory, input_file1);
-        }
-        if !input_file2.contains(&sep) && !input_file2.contains("/") {
-            input_file2 = format!("{}{}", working_directory, input_file2);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file1 = resolve_input_path(&input_file1, working_directory)?;
+        input_file2 = resolve_input_path(&input_file2, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
         if !output_file.ends_with(".html") {
             output_file = output_file + ".html";
         }
@@ -246,11 +191,9 @@ impl WhiteboxTool for TwoSampleKsTest {
         if input1.configs.rows != input2.configs.rows
             || input1.configs.columns != input2.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input1, &input2, verbose);
 
         let start = Instant::now();
         let mut progress: i32;
@@ -275,18 +218,19 @@ impl WhiteboxTool for TwoSampleKsTest {
             for row in 0..rows {
                 for col in 0..columns {
                     z1 = input1.get_value(row, col);
-                    if z1 != nodata1 {
+                    if !is_nodata(z1, nodata1) {
                         data1.push(z1);
                     }
                     z2 = input2.get_value(row, col);
-                    if z2 != nodata2 {
+                    if !is_nodata(z2, nodata2) {
                         data2.push(z2);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / rows as f64) as i32;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress as usize);
                         old_progress = progress;
                     }
                 }
@@ -303,15 +247,16 @@ impl WhiteboxTool for TwoSampleKsTest {
                 row = rng.gen_range(0, rows as isize);
                 col = rng.gen_range(0, columns as isize);
                 z1 = input1.get_value(row, col);
-                if z1 != nodata1 {
+                if !is_nodata(z1, nodata1) {
                     data1.push(z1);
 
                     sample_num += 1;
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * sample_num as f64 / num_samples as f64) as i32;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress as usize);
                             old_progress = progress;
                         }
                     }
@@ -323,15 +268,16 @@ impl WhiteboxTool for TwoSampleKsTest {
                 row = rng.gen_range(0, rows as isize);
                 col = rng.gen_range(0, columns as isize);
                 z2 = input2.get_value(row, col);
-                if z2 != nodata2 {
+                if !is_nodata(z2, nodata2) {
                     data2.push(z2);
 
                     sample_num += 1;
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * sample_num as f64 / num_samples as f64) as i32;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress as usize);
                             old_progress = progress;
                         }
                     }
@@ -356,6 +302,7 @@ impl WhiteboxTool for TwoSampleKsTest {
         let en2 = n2 as f64;
 
         while j1 < n1 && j2 < n2 {
+            reporter.check_cancelled()?;
             d1 = data1[j1];
             d2 = data2[j2];
             if d1 <= d2 {
@@ -12,7 +12,6 @@ only represent one statistic, given by the --stat flag.
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::cmp::Ordering::Equal;
 use std::env;
 use std::f64;
@@ -25,7 +24,7 @@ use std::path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to extract common descriptive statistics associated with the distribution
 /// of some underlying data raster based on feature units defined by a feature definition raster.
@@ -185,62 +184,22 @@ impl WhiteboxTool for ZonalStatistics {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut features_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut features_file = parsed.get_string("features").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         // let mut out_table = false;
         let mut output_html_file = String::new();
         let mut stat_type = String::from("mean");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_string("out_table") {
+            // out_table = true;
+            output_html_file = value.to_string();
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-features" {
-                features_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_table" {
-                // out_table = true;
-                output_html_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-stat" {
-                stat_type = if keyval {
-                    vec[1].to_string().to_lowercase()
-                } else {
-                    args[i + 1].to_string().to_lowercase()
-                };
-            }
+        if let Some(value) = parsed.get_string("stat") {
+            stat_type = value.to_string().to_lowercase();
         }
 
         if verbose {
@@ -254,26 +213,16 @@ impl WhiteboxTool for ZonalStatistics {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !features_file.contains(&sep) && !features_file.contains("/") {
-            features_file = format!("{}{}", working_directory, features_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        features_file = resolve_input_path(&features_file, working_directory)?;
         if !output_file.is_empty() {
-            if !output_file.contains(&sep) && !output_file.contains("/") {
-                output_file = format!("{}{}", working_directory, output_file);
-            }
+            output_file = resolve_path(&output_file, working_directory);
         }
         if !output_html_file.is_empty() {
-            if !output_html_file.contains(&sep) {
-                output_html_file = format!("{}{}", working_directory, output_html_file);
-            }
+            output_html_file = resolve_path(&output_html_file, working_directory);
         }
         if output_file.is_empty() && output_html_file.is_empty() {
             return Err(Error::new(
@@ -295,24 +244,17 @@ impl WhiteboxTool for ZonalStatistics {
         let features_nodata = features.configs.nodata;
 
         if features.configs.rows as isize != rows || features.configs.columns as isize != columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Input data and features definition raster must have the same dimensions.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("Input data and features definition raster must have the same dimensions."));
         }
 
         // How many features are there?
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let features = features.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut features_val: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut min_id = isize::max_value();
@@ -320,7 +262,7 @@ impl WhiteboxTool for ZonalStatistics {
                     let mut id: isize;
                     for col in 0..columns {
                         features_val = features.get_value(row, col);
-                        if features_val != features_nodata {
+                        if !is_nodata(features_val, features_nodata) {
                             id = features_val.round() as isize;
                             if id < min_id {
                                 min_id = id;
@@ -330,7 +272,9 @@ impl WhiteboxTool for ZonalStatistics {
                             }
                         }
                     }
-                    tx.send((min_id, max_id)).unwrap();
+                    if tx.send((min_id, max_id)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -346,10 +290,11 @@ impl WhiteboxTool for ZonalStatistics {
                 max_id = max;
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 1 of 3): {}%", progress);
+                    reporter.progress("Progress (Loop 1 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -378,7 +323,7 @@ impl WhiteboxTool for ZonalStatistics {
             for col in 0..columns {
                 val = input.get_value(row, col);
                 features_val = features.get_value(row, col);
-                if val != nodata && features_val != features_nodata {
+                if !is_nodata(val, nodata) && !is_nodata(features_val, features_nodata) {
                     id = (features_val.round() as isize - min_id) as usize;
                     features_data[id].push(val);
                     features_present[id] = true;
@@ -392,16 +337,18 @@ impl WhiteboxTool for ZonalStatistics {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 3): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 3)", progress);
                     old_progress = progress;
                 }
             }
         }
 
         for id in 0..num_features {
+            reporter.check_cancelled()?;
             if features_n[id] > 0f64 {
                 features_average[id] = features_total[id] / features_n[id];
                 features_range[id] = features_max[id] - features_min[id];
@@ -412,16 +359,17 @@ impl WhiteboxTool for ZonalStatistics {
             for col in 0..columns {
                 val = input.get_value(row, col);
                 features_val = features.get_value(row, col);
-                if val != nodata && features_val != features_nodata {
+                if !is_nodata(val, nodata) && !is_nodata(features_val, features_nodata) {
                     id = (features_val.round() as isize - min_id) as usize;
                     features_total_deviation[id] +=
                         (val - features_average[id]) * (val - features_average[id]);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 3 of 3): {}%", progress);
+                    reporter.progress("Progress (Loop 3 of 3)", progress);
                     old_progress = progress;
                 }
             }
@@ -431,6 +379,7 @@ impl WhiteboxTool for ZonalStatistics {
             println!("Calculating medians...");
         }
         for id in 0..num_features {
+            reporter.check_cancelled()?;
             if features_n[id] > 1f64 {
                 features_std_deviation[id] =
                     (features_total_deviation[id] / (features_n[id] - 1f64)).sqrt();
@@ -474,15 +423,16 @@ impl WhiteboxTool for ZonalStatistics {
                 for col in 0..columns {
                     val = input.get_value(row, col);
                     features_val = features.get_value(row, col);
-                    if val != nodata && features_val != features_nodata {
+                    if !is_nodata(val, nodata) && !is_nodata(features_val, features_nodata) {
                         id = (features_val.round() as isize - min_id) as usize;
                         output.set_value(row, col, out_stat[id]);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Output: {}%", progress);
+                        reporter.progress("Output", progress);
                         old_progress = progress;
                     }
                 }
@@ -608,6 +558,7 @@ impl WhiteboxTool for ZonalStatistics {
 
             // data
             for id in 0..num_features {
+                reporter.check_cancelled()?;
                 if features_n[id] > 0f64 {
                     writer.write_all(
                         &format!(
@@ -4,16 +4,38 @@ pub mod hydro_analysis;
 pub mod image_analysis;
 pub mod lidar_analysis;
 pub mod math_stat_analysis;
+pub mod precision_agriculture;
 pub mod stream_network_analysis;
 pub mod terrain_analysis;
 
-use whitebox_common::utils::get_formatted_elapsed_time;
+mod benchmark;
+mod gpu;
+mod json_events;
+mod presets;
+mod progress;
+mod provenance;
+mod run_manifest;
+mod self_test;
+mod server;
+
+pub use self::server::DEFAULT_SERVER_ADDRESS;
+pub use self::progress::{
+    recv_data, CancellableProgress, CancellationToken, ConsoleProgress, JsonProgress,
+    ProgressReporter, WorkerMessage,
+};
+use whitebox_common::utils::{
+    available_threads, get_formatted_elapsed_time, record_threads_used, resolve_input_path,
+    resolve_path, ThreadPool,
+};
+use whitebox_raster::{PhotometricInterpretation, Raster, RasterConfigs};
+use whitebox_common::errors::ErrorCause;
 use serde_json;
 use std::io::{Error, ErrorKind};
 use std::time::Instant;
 use std::path;
 use std::fs;
 use std::collections::HashMap;
+use std::panic;
 use std::process::Command;
 use std::env;
 // use std::io;
@@ -24,6 +46,8 @@ pub struct ToolManager {
     pub working_dir: String,
     pub verbose: bool,
     tool_names: Vec<String>,
+    progress: Option<Box<dyn ProgressReporter>>,
+    cancellation: Option<CancellationToken>,
 }
 
 impl ToolManager {
@@ -35,43 +59,62 @@ impl ToolManager {
         // data_tools
         tool_names.push("AddPointCoordinatesToTable".to_string());
         tool_names.push("CleanVector".to_string());
+        tool_names.push("CompareRasters".to_string());
+        tool_names.push("CompareVectors".to_string());
         tool_names.push("ConvertNodataToZero".to_string());
         tool_names.push("ConvertRasterFormat".to_string());
         tool_names.push("CsvPointsToVector".to_string());
+        tool_names.push("ExportStacItems".to_string());
         tool_names.push("ExportTableToCsv".to_string());
+        tool_names.push("HexToSquareGrid".to_string());
         tool_names.push("JoinTables".to_string());
+        tool_names.push("LinesToPoints".to_string());
         tool_names.push("LinesToPolygons".to_string());
         tool_names.push("MergeTableWithCsv".to_string());
         tool_names.push("MergeVectors".to_string());
         tool_names.push("ModifyNoDataValue".to_string());
         tool_names.push("MultiPartToSinglePart".to_string());
         tool_names.push("NewRasterFromBase".to_string());
+        tool_names.push("Pipeline".to_string());
+        tool_names.push("PointsToLines".to_string());
         tool_names.push("PolygonsToLines".to_string());
         tool_names.push("PrintGeoTiffTags".to_string());
+        tool_names.push("RasterSummary".to_string());
         tool_names.push("RasterToVectorLines".to_string());
         tool_names.push("RasterToVectorPoints".to_string());
         tool_names.push("RasterToVectorPolygons".to_string());
+        tool_names.push("RasterToXyz".to_string());
         tool_names.push("ReinitializeAttributeTable".to_string());
         tool_names.push("RemovePolygonHoles".to_string());
+        tool_names.push("ReprojectRaster".to_string());
         tool_names.push("SetNodataValue".to_string());
         tool_names.push("SinglePartToMultiPart".to_string());
+        tool_names.push("SplitVectorLayer".to_string());
+        tool_names.push("SquareToHexGrid".to_string());
+        tool_names.push("StacSearch".to_string());
+        tool_names.push("TranslateNodata".to_string());
         tool_names.push("VectorLinesToRaster".to_string());
         tool_names.push("VectorPointsToRaster".to_string());
         tool_names.push("VectorPolygonsToRaster".to_string());
+        tool_names.push("XyzToRaster".to_string());
 
         // gis_analysis
         tool_names.push("AggregateRaster".to_string());
+        tool_names.push("ArealInterpolation".to_string());
         tool_names.push("AverageOverlay".to_string());
         tool_names.push("BlockMaximumGridding".to_string());
         tool_names.push("BlockMinimumGridding".to_string());
+        tool_names.push("BlockStatistics".to_string());
         tool_names.push("BoundaryShapeComplexity".to_string());
         tool_names.push("BufferRaster".to_string());
         // tool_names.push("BufferVector".to_string());
+        tool_names.push("CalibrateRoutes".to_string());
         tool_names.push("Centroid".to_string());
         tool_names.push("CentroidVector".to_string());
         tool_names.push("Clip".to_string());
         tool_names.push("ClipRasterToPolygon".to_string());
         tool_names.push("Clump".to_string());
+        tool_names.push("Combine".to_string());
         tool_names.push("CompactnessRatio".to_string());
         tool_names.push("ConstructVectorTIN".to_string());
         tool_names.push("CountIf".to_string());
@@ -82,6 +125,7 @@ impl ToolManager {
         tool_names.push("CreatePlane".to_string());
         tool_names.push("CreateRectangularVectorGrid".to_string());
         tool_names.push("Difference".to_string());
+        tool_names.push("DisaggregateRaster".to_string());
         tool_names.push("Dissolve".to_string());
         tool_names.push("EdgeProportion".to_string());
         tool_names.push("EliminateCoincidentPoints".to_string());
@@ -93,6 +137,7 @@ impl ToolManager {
         tool_names.push("ExtendVectorLines".to_string());
         tool_names.push("ExtractNodes".to_string());
         tool_names.push("ExtractRasterValuesAtPoints".to_string());
+        tool_names.push("FillNodataFromRasters".to_string());
         tool_names.push("FilterRasterFeaturesByArea".to_string());
         tool_names.push("FindLowestOrHighestPoints".to_string());
         tool_names.push("FindPatchOrClassEdgeCells".to_string());
@@ -101,11 +146,15 @@ impl ToolManager {
         tool_names.push("IdwInterpolation".to_string());
         tool_names.push("Intersect".to_string());
         tool_names.push("LayerFootprint".to_string());
+        tool_names.push("LeastCostPathDensity".to_string());
         tool_names.push("LinearityIndex".to_string());
         tool_names.push("LineIntersections".to_string());
+        tool_names.push("LocateRouteEvents".to_string());
         tool_names.push("LowestPosition".to_string());
         tool_names.push("MaxAbsoluteOverlay".to_string());
         tool_names.push("MaxOverlay".to_string());
+        tool_names.push("MeanCenter".to_string());
+        tool_names.push("MedianCenter".to_string());
         tool_names.push("Medoid".to_string());
         tool_names.push("MergeLineSegments".to_string());
         tool_names.push("MinAbsoluteOverlay".to_string());
@@ -141,7 +190,10 @@ impl ToolManager {
         tool_names.push("ShapeComplexityIndex".to_string());
         tool_names.push("ShapeComplexityIndexRaster".to_string());
         tool_names.push("SmoothVectors".to_string());
+        tool_names.push("SnapVectors".to_string());
         tool_names.push("SplitWithLines".to_string());
+        tool_names.push("StandardDeviationalEllipse".to_string());
+        tool_names.push("StandardDistance".to_string());
         tool_names.push("SumOverlay".to_string());
         tool_names.push("SymmetricalDifference".to_string());
         tool_names.push("TINGridding".to_string());
@@ -184,6 +236,7 @@ impl ToolManager {
         tool_names.push("FloodOrder".to_string());
         tool_names.push("FlowAccumulationFullWorkflow".to_string());
         tool_names.push("FlowLengthDiff".to_string());
+        tool_names.push("HexFlowAccumulation".to_string());
         tool_names.push("Hillslopes".to_string());
         tool_names.push("ImpoundmentSizeIndex".to_string());
         tool_names.push("InsertDams".to_string());
@@ -194,12 +247,15 @@ impl ToolManager {
         tool_names.push("MDInfFlowAccumulation".to_string());
         tool_names.push("NumInflowingNeighbours".to_string());
         tool_names.push("RaiseWalls".to_string());
+        tool_names.push("RelativeElevationModel".to_string());
         tool_names.push("Rho8Pointer".to_string());
         tool_names.push("Sink".to_string());
         tool_names.push("SnapPourPoints".to_string());
+        tool_names.push("SoilMoistureDownscaling".to_string());
         tool_names.push("StochasticDepressionAnalysis".to_string());
         tool_names.push("StrahlerOrderBasins".to_string());
         tool_names.push("Subbasins".to_string());
+        tool_names.push("TopoToRaster".to_string());
         tool_names.push("TraceDownslopeFlowpaths".to_string());
         tool_names.push("UnnestBasins".to_string());
         tool_names.push("UpslopeDepressionStorage".to_string());
@@ -209,8 +265,11 @@ impl ToolManager {
         tool_names.push("AdaptiveFilter".to_string());
         tool_names.push("BalanceContrastEnhancement".to_string());
         tool_names.push("BilateralFilter".to_string());
+        tool_names.push("BuildVirtualMosaic".to_string());
+        tool_names.push("BurnSeverity".to_string());
         tool_names.push("ChangeVectorAnalysis".to_string());
         tool_names.push("Closing".to_string());
+        tool_names.push("ColourizeRaster".to_string());
         tool_names.push("ConservativeSmoothingFilter".to_string());
         tool_names.push("CornerDetection".to_string());
         tool_names.push("CorrectVignetting".to_string());
@@ -220,6 +279,9 @@ impl ToolManager {
         tool_names.push("DiffOfGaussianFilter".to_string());
         tool_names.push("EdgePreservingMeanFilter".to_string());
         tool_names.push("EmbossFilter".to_string());
+        tool_names.push("ExportColourComposite".to_string());
+        tool_names.push("ExportImageChips".to_string());
+        tool_names.push("ExportWebTiles".to_string());
         tool_names.push("FastAlmostGaussianFilter".to_string());
         tool_names.push("FlipImage".to_string());
         tool_names.push("GammaCorrection".to_string());
@@ -230,6 +292,7 @@ impl ToolManager {
         tool_names.push("HistogramEqualization".to_string());
         tool_names.push("HistogramMatching".to_string());
         tool_names.push("HistogramMatchingTwoImages".to_string());
+        tool_names.push("HoughTransform".to_string());
         tool_names.push("IhsToRgb".to_string());
         tool_names.push("ImageStackProfile".to_string());
         tool_names.push("IntegralImage".to_string());
@@ -247,28 +310,39 @@ impl ToolManager {
         tool_names.push("MinMaxContrastStretch".to_string());
         tool_names.push("MinimumFilter".to_string());
         tool_names.push("ModifiedKMeansClustering".to_string());
+        tool_names.push("MorphologicalFilter".to_string());
+        tool_names.push("MorphologicalReconstruction".to_string());
         tool_names.push("Mosaic".to_string());
         tool_names.push("MosaicWithFeathering".to_string());
         tool_names.push("NormalizedDifferenceIndex".to_string());
         tool_names.push("OlympicFilter".to_string());
+        tool_names.push("OnnxInference".to_string());
         tool_names.push("Opening".to_string());
         tool_names.push("PanchromaticSharpening".to_string());
         tool_names.push("PercentageContrastStretch".to_string());
         tool_names.push("PercentileFilter".to_string());
         tool_names.push("PrewittFilter".to_string());
+        tool_names.push("QuickView".to_string());
         tool_names.push("RangeFilter".to_string());
         tool_names.push("RemoveSpurs".to_string());
         tool_names.push("Resample".to_string());
         tool_names.push("RgbToIhs".to_string());
         tool_names.push("RobertsCrossFilter".to_string());
+        tool_names.push("SarDecibelConversion".to_string());
+        tool_names.push("SarSpeckleFilter".to_string());
         tool_names.push("ScharrFilter".to_string());
         tool_names.push("SigmoidalContrastStretch".to_string());
+        tool_names.push("Skeletonize".to_string());
         tool_names.push("SobelFilter".to_string());
+        tool_names.push("SpectralIndices".to_string());
         tool_names.push("SplitColourComposite".to_string());
         tool_names.push("StandardDeviationContrastStretch".to_string());
+        tool_names.push("SubpixelBoundary".to_string());
+        tool_names.push("SurfaceWaterExtraction".to_string());
         tool_names.push("StandardDeviationFilter".to_string());
         tool_names.push("ThickenRasterLine".to_string());
         tool_names.push("TophatTransform".to_string());
+        tool_names.push("TopographicCorrection".to_string());
         tool_names.push("TotalFilter".to_string());
         tool_names.push("UnsharpMasking".to_string());
         tool_names.push("UserDefinedWeightsFilter".to_string());
@@ -354,10 +428,14 @@ impl ToolManager {
         tool_names.push("Exp2".to_string());
         tool_names.push("ZonalStatistics".to_string());
         tool_names.push("Floor".to_string());
+        tool_names.push("GlobalAutocorrelation".to_string());
         tool_names.push("GreaterThan".to_string());
+        tool_names.push("HotSpotAnalysis".to_string());
         tool_names.push("ImageAutocorrelation".to_string());
         tool_names.push("ImageCorrelation".to_string());
         tool_names.push("ImageCorrelationNeighbourhoodAnalysis".to_string());
+        tool_names.push("ImageGlobalAutocorrelation".to_string());
+        tool_names.push("ImageHotSpotAnalysis".to_string());
         tool_names.push("ImageRegression".to_string());
         tool_names.push("Increment".to_string());
         tool_names.push("InPlaceAdd".to_string());
@@ -411,8 +489,14 @@ impl ToolManager {
         tool_names.push("Xor".to_string());
         tool_names.push("ZScores".to_string());
 
+        // precision_agriculture
+        tool_names.push("CleanYieldData".to_string());
+        tool_names.push("ManagementZones".to_string());
+
         // stream_network_analysis
+        tool_names.push("ConformStreamsToHydrography".to_string());
         tool_names.push("DistanceToOutlet".to_string());
+        tool_names.push("DrainageDensity".to_string());
         tool_names.push("ExtractStreams".to_string());
         tool_names.push("ExtractValleys".to_string());
         tool_names.push("FarthestChannelHead".to_string());
@@ -432,17 +516,22 @@ impl ToolManager {
         tool_names.push("StreamLinkLength".to_string());
         tool_names.push("StreamLinkSlope".to_string());
         tool_names.push("StreamSlopeContinuous".to_string());
+        tool_names.push("StreamSolarExposure".to_string());
         tool_names.push("TopologicalStreamOrder".to_string());
         tool_names.push("TributaryIdentifier".to_string());
 
         // terrain_analysis
+        tool_names.push("AccumulationCurvature".to_string());
         tool_names.push("Aspect".to_string());
         tool_names.push("AverageNormalVectorAngularDeviation".to_string());
+        tool_names.push("BathymetricPositionIndex".to_string());
+        tool_names.push("BenthicTerrainClassification".to_string());
         tool_names.push("CircularVarianceOfAspect".to_string());
         tool_names.push("ContoursFromPoints".to_string());
         tool_names.push("ContoursFromRaster".to_string());
         tool_names.push("DevFromMeanElev".to_string());
         tool_names.push("DiffFromMeanElev".to_string());
+        tool_names.push("DifferenceCurvature".to_string());
         tool_names.push("DirectionalRelief".to_string());
         tool_names.push("DownslopeIndex".to_string());
         tool_names.push("EdgeDensity".to_string());
@@ -455,12 +544,18 @@ impl ToolManager {
         tool_names.push("FetchAnalysis".to_string());
         tool_names.push("FillMissingData".to_string());
         tool_names.push("FindRidges".to_string());
+        tool_names.push("FractalTerrain".to_string());
         tool_names.push("GaussianCurvature".to_string());
+        tool_names.push("GaussianHills".to_string());
         tool_names.push("Geomorphons".to_string());
+        tool_names.push("GlacierFlowlines".to_string());
+        tool_names.push("GlacierHypsometry".to_string());
         tool_names.push("Hillshade".to_string());
         tool_names.push("HorizonAngle".to_string());
+        tool_names.push("HorizontalExcessCurvature".to_string());
         tool_names.push("HypsometricAnalysis".to_string());
         tool_names.push("HypsometricallyTintedHillshade".to_string());
+        tool_names.push("LocalDominance".to_string());
         tool_names.push("MapOffTerrainObjects".to_string());
         tool_names.push("MaxAnisotropyDev".to_string());
         tool_names.push("MaxAnisotropyDevSignature".to_string());
@@ -483,8 +578,10 @@ impl ToolManager {
         tool_names.push("MultiscaleTopographicPositionImage".to_string());
         tool_names.push("NumDownslopeNeighbours".to_string());
         tool_names.push("NumUpslopeNeighbours".to_string());
+        tool_names.push("Openness".to_string());
         tool_names.push("PennockLandformClass".to_string());
         tool_names.push("PercentElevRange".to_string());
+        tool_names.push("PerlinNoiseTerrain".to_string());
         tool_names.push("PlanCurvature".to_string());
         tool_names.push("ProfileCurvature".to_string());
         tool_names.push("Profile".to_string());
@@ -492,16 +589,24 @@ impl ToolManager {
         tool_names.push("StreamPowerIndex".to_string());
         tool_names.push("RelativeTopographicPosition".to_string());
         tool_names.push("RemoveOffTerrainObjects".to_string());
+        tool_names.push("RingCurvature".to_string());
+        tool_names.push("Rotor".to_string());
         tool_names.push("RuggednessIndex".to_string());
         tool_names.push("TimeInDaylight".to_string());
         tool_names.push("SedimentTransportIndex".to_string());
+        tool_names.push("ShallowLandslideSusceptibility".to_string());
+        tool_names.push("SkyViewFactor".to_string());
         tool_names.push("Slope".to_string());
+        tool_names.push("SlopePositionClassification".to_string());
         tool_names.push("SlopeVsElevationPlot".to_string());
         tool_names.push("SphericalStdDevOfNormals".to_string());
         tool_names.push("StandardDeviationOfSlope".to_string());
         tool_names.push("SurfaceAreaRatio".to_string());
+        tool_names.push("SyntheticValley".to_string());
         tool_names.push("TangentialCurvature".to_string());
+        tool_names.push("TerrainAdjustedDegreeDays".to_string());
         tool_names.push("TotalCurvature".to_string());
+        tool_names.push("VerticalExcessCurvature".to_string());
         tool_names.push("Viewshed".to_string());
         tool_names.push("VisibilityIndex".to_string());
         tool_names.push("WetnessIndex".to_string());
@@ -512,10 +617,31 @@ impl ToolManager {
             working_dir: working_directory.to_string(),
             verbose: *verbose_mode,
             tool_names: tool_names,
+            progress: None,
+            cancellation: None,
         };
         Ok(tm)
     }
 
+    /// Sets the reporter that receives the progress of the tools that are run, in verbose mode.
+    /// By default, progress is printed to the standard output (`ConsoleProgress`).
+    pub fn set_progress_reporter(&mut self, reporter: Box<dyn ProgressReporter>) {
+        self.progress = Some(reporter);
+    }
+
+    /// Sets a token with which another thread can cancel the tools that are run. A cancelled
+    /// tool stops at its next cancellation check and returns an `Interrupted` error.
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation = Some(token);
+    }
+
+    fn progress_reporter(&self) -> &dyn ProgressReporter {
+        match &self.progress {
+            Some(reporter) => reporter.as_ref(),
+            None => &ConsoleProgress,
+        }
+    }
+
     fn get_tool(&self, tool_name: &str) -> Option<Box<dyn WhiteboxTool + 'static>> {
         match tool_name.to_lowercase().replace("_", "").as_ref() {
             // data_tools
@@ -523,47 +649,66 @@ impl ToolManager {
                 Some(Box::new(data_tools::AddPointCoordinatesToTable::new()))
             }
             "cleanvector" => Some(Box::new(data_tools::CleanVector::new())),
+            "comparerasters" => Some(Box::new(data_tools::CompareRasters::new())),
+            "comparevectors" => Some(Box::new(data_tools::CompareVectors::new())),
             "convertnodatatozero" => Some(Box::new(data_tools::ConvertNodataToZero::new())),
             "convertrasterformat" => Some(Box::new(data_tools::ConvertRasterFormat::new())),
             "csvpointstovector" => Some(Box::new(data_tools::CsvPointsToVector::new())),
+            "exportstacitems" => Some(Box::new(data_tools::ExportStacItems::new())),
             "exporttabletocsv" => Some(Box::new(data_tools::ExportTableToCsv::new())),
+            "hextosquaregrid" => Some(Box::new(data_tools::HexToSquareGrid::new())),
             "jointables" => Some(Box::new(data_tools::JoinTables::new())),
+            "linestopoints" => Some(Box::new(data_tools::LinesToPoints::new())),
             "linestopolygons" => Some(Box::new(data_tools::LinesToPolygons::new())),
             "mergetablewithcsv" => Some(Box::new(data_tools::MergeTableWithCsv::new())),
             "mergevectors" => Some(Box::new(data_tools::MergeVectors::new())),
             "modifynodatavalue" => Some(Box::new(data_tools::ModifyNoDataValue::new())),
             "multiparttosinglepart" => Some(Box::new(data_tools::MultiPartToSinglePart::new())),
             "newrasterfrombase" => Some(Box::new(data_tools::NewRasterFromBase::new())),
+            "pipeline" => Some(Box::new(data_tools::Pipeline::new())),
+            "pointstolines" => Some(Box::new(data_tools::PointsToLines::new())),
             "polygonstolines" => Some(Box::new(data_tools::PolygonsToLines::new())),
             "printgeotifftags" => Some(Box::new(data_tools::PrintGeoTiffTags::new())),
+            "rastersummary" => Some(Box::new(data_tools::RasterSummary::new())),
             "rastertovectorlines" => Some(Box::new(data_tools::RasterToVectorLines::new())),
             "rastertovectorpoints" => Some(Box::new(data_tools::RasterToVectorPoints::new())),
             "rastertovectorpolygons" => Some(Box::new(data_tools::RasterToVectorPolygons::new())),
+            "rastertoxyz" => Some(Box::new(data_tools::RasterToXyz::new())),
             "reinitializeattributetable" => {
                 Some(Box::new(data_tools::ReinitializeAttributeTable::new()))
             }
             "removepolygonholes" => Some(Box::new(data_tools::RemovePolygonHoles::new())),
+            "reprojectraster" => Some(Box::new(data_tools::ReprojectRaster::new())),
             "setnodatavalue" => Some(Box::new(data_tools::SetNodataValue::new())),
             "singleparttomultipart" => Some(Box::new(data_tools::SinglePartToMultiPart::new())),
+            "splitvectorlayer" => Some(Box::new(data_tools::SplitVectorLayer::new())),
+            "squaretohexgrid" => Some(Box::new(data_tools::SquareToHexGrid::new())),
+            "stacsearch" => Some(Box::new(data_tools::StacSearch::new())),
+            "translatenodata" => Some(Box::new(data_tools::TranslateNodata::new())),
             "vectorlinestoraster" => Some(Box::new(data_tools::VectorLinesToRaster::new())),
             "vectorpointstoraster" => Some(Box::new(data_tools::VectorPointsToRaster::new())),
             "vectorpolygonstoraster" => Some(Box::new(data_tools::VectorPolygonsToRaster::new())),
+            "xyztoraster" => Some(Box::new(data_tools::XyzToRaster::new())),
 
             // gis_analysis
             "aggregateraster" => Some(Box::new(gis_analysis::AggregateRaster::new())),
+            "arealinterpolation" => Some(Box::new(gis_analysis::ArealInterpolation::new())),
             "averageoverlay" => Some(Box::new(gis_analysis::AverageOverlay::new())),
             "blockmaximumgridding" => Some(Box::new(gis_analysis::BlockMaximumGridding::new())),
             "blockminimumgridding" => Some(Box::new(gis_analysis::BlockMinimumGridding::new())),
+            "blockstatistics" => Some(Box::new(gis_analysis::BlockStatistics::new())),
             "boundaryshapecomplexity" => {
                 Some(Box::new(gis_analysis::BoundaryShapeComplexity::new()))
             }
             "bufferraster" => Some(Box::new(gis_analysis::BufferRaster::new())),
             // "buffervector" => Some(Box::new(gis_analysis::BufferVector::new())),
+            "calibrateroutes" => Some(Box::new(gis_analysis::CalibrateRoutes::new())),
             "centroid" => Some(Box::new(gis_analysis::Centroid::new())),
             "centroidvector" => Some(Box::new(gis_analysis::CentroidVector::new())),
             "clip" => Some(Box::new(gis_analysis::Clip::new())),
             "cliprastertopolygon" => Some(Box::new(gis_analysis::ClipRasterToPolygon::new())),
             "clump" => Some(Box::new(gis_analysis::Clump::new())),
+            "combine" => Some(Box::new(gis_analysis::Combine::new())),
             "compactnessratio" => Some(Box::new(gis_analysis::CompactnessRatio::new())),
             "constructvectortin" => Some(Box::new(gis_analysis::ConstructVectorTIN::new())),
             "countif" => Some(Box::new(gis_analysis::CountIf::new())),
@@ -578,6 +723,7 @@ impl ToolManager {
                 Some(Box::new(gis_analysis::CreateRectangularVectorGrid::new()))
             }
             "difference" => Some(Box::new(gis_analysis::Difference::new())),
+            "disaggregateraster" => Some(Box::new(gis_analysis::DisaggregateRaster::new())),
             "dissolve" => Some(Box::new(gis_analysis::Dissolve::new())),
             "edgeproportion" => Some(Box::new(gis_analysis::EdgeProportion::new())),
             "eliminatecoincidentpoints" => {
@@ -593,6 +739,9 @@ impl ToolManager {
             "extractrastervaluesatpoints" => {
                 Some(Box::new(gis_analysis::ExtractRasterValuesAtPoints::new()))
             }
+            "fillnodatafromrasters" => {
+                Some(Box::new(gis_analysis::FillNodataFromRasters::new()))
+            }
             "filterrasterfeaturesbyarea" => {
                 Some(Box::new(gis_analysis::FilterRasterFeaturesByArea::new()))
             }
@@ -607,11 +756,15 @@ impl ToolManager {
             "idwinterpolation" => Some(Box::new(gis_analysis::IdwInterpolation::new())),
             "intersect" => Some(Box::new(gis_analysis::Intersect::new())),
             "layerfootprint" => Some(Box::new(gis_analysis::LayerFootprint::new())),
+            "leastcostpathdensity" => Some(Box::new(gis_analysis::LeastCostPathDensity::new())),
             "lineintersections" => Some(Box::new(gis_analysis::LineIntersections::new())),
             "linearityindex" => Some(Box::new(gis_analysis::LinearityIndex::new())),
+            "locaterouteevents" => Some(Box::new(gis_analysis::LocateRouteEvents::new())),
             "lowestposition" => Some(Box::new(gis_analysis::LowestPosition::new())),
             "maxabsoluteoverlay" => Some(Box::new(gis_analysis::MaxAbsoluteOverlay::new())),
             "maxoverlay" => Some(Box::new(gis_analysis::MaxOverlay::new())),
+            "meancenter" => Some(Box::new(gis_analysis::MeanCenter::new())),
+            "mediancenter" => Some(Box::new(gis_analysis::MedianCenter::new())),
             "medoid" => Some(Box::new(gis_analysis::Medoid::new())),
             "mergelinesegments" => Some(Box::new(gis_analysis::MergeLineSegments::new())),
             "minabsoluteoverlay" => Some(Box::new(gis_analysis::MinAbsoluteOverlay::new())),
@@ -659,7 +812,12 @@ impl ToolManager {
                 Some(Box::new(gis_analysis::ShapeComplexityIndexRaster::new()))
             }
             "smoothvectors" => Some(Box::new(gis_analysis::SmoothVectors::new())),
+            "snapvectors" => Some(Box::new(gis_analysis::SnapVectors::new())),
             "splitwithlines" => Some(Box::new(gis_analysis::SplitWithLines::new())),
+            "standarddeviationalellipse" => {
+                Some(Box::new(gis_analysis::StandardDeviationalEllipse::new()))
+            }
+            "standarddistance" => Some(Box::new(gis_analysis::StandardDistance::new())),
             "sumoverlay" => Some(Box::new(gis_analysis::SumOverlay::new())),
             "symmetricaldifference" => Some(Box::new(gis_analysis::SymmetricalDifference::new())),
             "tingridding" => Some(Box::new(gis_analysis::TINGridding::new())),
@@ -718,6 +876,7 @@ impl ToolManager {
                 Some(Box::new(hydro_analysis::FlowAccumulationFullWorkflow::new()))
             }
             "flowlengthdiff" => Some(Box::new(hydro_analysis::FlowLengthDiff::new())),
+            "hexflowaccumulation" => Some(Box::new(hydro_analysis::HexFlowAccumulation::new())),
             "hillslopes" => Some(Box::new(hydro_analysis::Hillslopes::new())),
             "impoundmentsizeindex" => Some(Box::new(hydro_analysis::ImpoundmentSizeIndex::new())),
             "insertdams" => Some(Box::new(hydro_analysis::InsertDams::new())),
@@ -732,14 +891,21 @@ impl ToolManager {
                 Some(Box::new(hydro_analysis::NumInflowingNeighbours::new()))
             }
             "raisewalls" => Some(Box::new(hydro_analysis::RaiseWalls::new())),
+            "relativeelevationmodel" => {
+                Some(Box::new(hydro_analysis::RelativeElevationModel::new()))
+            }
             "rho8pointer" => Some(Box::new(hydro_analysis::Rho8Pointer::new())),
             "sink" => Some(Box::new(hydro_analysis::Sink::new())),
             "snappourpoints" => Some(Box::new(hydro_analysis::SnapPourPoints::new())),
+            "soilmoisturedownscaling" => {
+                Some(Box::new(hydro_analysis::SoilMoistureDownscaling::new()))
+            }
             "stochasticdepressionanalysis" => {
                 Some(Box::new(hydro_analysis::StochasticDepressionAnalysis::new()))
             }
             "strahlerorderbasins" => Some(Box::new(hydro_analysis::StrahlerOrderBasins::new())),
             "subbasins" => Some(Box::new(hydro_analysis::Subbasins::new())),
+            "topotoraster" => Some(Box::new(hydro_analysis::TopoToRaster::new())),
             "tracedownslopeflowpaths" => {
                 Some(Box::new(hydro_analysis::TraceDownslopeFlowpaths::new()))
             }
@@ -755,8 +921,11 @@ impl ToolManager {
                 Some(Box::new(image_analysis::BalanceContrastEnhancement::new()))
             }
             "bilateralfilter" => Some(Box::new(image_analysis::BilateralFilter::new())),
+            "buildvirtualmosaic" => Some(Box::new(image_analysis::BuildVirtualMosaic::new())),
+            "burnseverity" => Some(Box::new(image_analysis::BurnSeverity::new())),
             "changevectoranalysis" => Some(Box::new(image_analysis::ChangeVectorAnalysis::new())),
             "closing" => Some(Box::new(image_analysis::Closing::new())),
+            "colourizeraster" => Some(Box::new(image_analysis::ColourizeRaster::new())),
             "cornerdetection" => Some(Box::new(image_analysis::CornerDetection::new())),
             "correctvignetting" => Some(Box::new(image_analysis::CorrectVignetting::new())),
             "conservativesmoothingfilter" => {
@@ -772,6 +941,9 @@ impl ToolManager {
                 Some(Box::new(image_analysis::EdgePreservingMeanFilter::new()))
             }
             "embossfilter" => Some(Box::new(image_analysis::EmbossFilter::new())),
+            "exportcolourcomposite" => Some(Box::new(image_analysis::ExportColourComposite::new())),
+            "exportimagechips" => Some(Box::new(image_analysis::ExportImageChips::new())),
+            "exportwebtiles" => Some(Box::new(image_analysis::ExportWebTiles::new())),
             "fastalmostgaussianfilter" => {
                 Some(Box::new(image_analysis::FastAlmostGaussianFilter::new()))
             }
@@ -788,6 +960,7 @@ impl ToolManager {
             "histogrammatchingtwoimages" => {
                 Some(Box::new(image_analysis::HistogramMatchingTwoImages::new()))
             }
+            "houghtransform" => Some(Box::new(image_analysis::HoughTransform::new())),
             "ihstorgb" => Some(Box::new(image_analysis::IhsToRgb::new())),
             "imagestackprofile" => Some(Box::new(image_analysis::ImageStackProfile::new())),
             "integralimage" => Some(Box::new(image_analysis::IntegralImage::new())),
@@ -809,12 +982,17 @@ impl ToolManager {
             "modifiedkmeansclustering" => {
                 Some(Box::new(image_analysis::ModifiedKMeansClustering::new()))
             }
+            "morphologicalfilter" => Some(Box::new(image_analysis::MorphologicalFilter::new())),
+            "morphologicalreconstruction" => {
+                Some(Box::new(image_analysis::MorphologicalReconstruction::new()))
+            }
             "mosaic" => Some(Box::new(image_analysis::Mosaic::new())),
             "mosaicwithfeathering" => Some(Box::new(image_analysis::MosaicWithFeathering::new())),
             "normalizeddifferenceindex" => {
                 Some(Box::new(image_analysis::NormalizedDifferenceIndex::new()))
             }
             "olympicfilter" => Some(Box::new(image_analysis::OlympicFilter::new())),
+            "onnxinference" => Some(Box::new(image_analysis::OnnxInference::new())),
             "opening" => Some(Box::new(image_analysis::Opening::new())),
             "panchromaticsharpening" => {
                 Some(Box::new(image_analysis::PanchromaticSharpening::new()))
@@ -824,25 +1002,33 @@ impl ToolManager {
             }
             "percentilefilter" => Some(Box::new(image_analysis::PercentileFilter::new())),
             "prewittfilter" => Some(Box::new(image_analysis::PrewittFilter::new())),
+            "quickview" => Some(Box::new(image_analysis::QuickView::new())),
             "rangefilter" => Some(Box::new(image_analysis::RangeFilter::new())),
             "removespurs" => Some(Box::new(image_analysis::RemoveSpurs::new())),
             "resample" => Some(Box::new(image_analysis::Resample::new())),
             "rgbtoihs" => Some(Box::new(image_analysis::RgbToIhs::new())),
             "robertscrossfilter" => Some(Box::new(image_analysis::RobertsCrossFilter::new())),
+            "sardecibelconversion" => Some(Box::new(image_analysis::SarDecibelConversion::new())),
+            "sarspecklefilter" => Some(Box::new(image_analysis::SarSpeckleFilter::new())),
             "scharrfilter" => Some(Box::new(image_analysis::ScharrFilter::new())),
             "sigmoidalcontraststretch" => {
                 Some(Box::new(image_analysis::SigmoidalContrastStretch::new()))
             }
+            "skeletonize" => Some(Box::new(image_analysis::Skeletonize::new())),
             "sobelfilter" => Some(Box::new(image_analysis::SobelFilter::new())),
+            "spectralindices" => Some(Box::new(image_analysis::SpectralIndices::new())),
             "splitcolourcomposite" => Some(Box::new(image_analysis::SplitColourComposite::new())),
             "standarddeviationcontraststretch" => Some(Box::new(
                 image_analysis::StandardDeviationContrastStretch::new(),
             )),
+            "subpixelboundary" => Some(Box::new(image_analysis::SubpixelBoundary::new())),
+            "surfacewaterextraction" => Some(Box::new(image_analysis::SurfaceWaterExtraction::new())),
             "standarddeviationfilter" => {
                 Some(Box::new(image_analysis::StandardDeviationFilter::new()))
             }
             "thickenrasterline" => Some(Box::new(image_analysis::ThickenRasterLine::new())),
             "tophattransform" => Some(Box::new(image_analysis::TophatTransform::new())),
+            "topographiccorrection" => Some(Box::new(image_analysis::TopographicCorrection::new())),
             "totalfilter" => Some(Box::new(image_analysis::TotalFilter::new())),
             "unsharpmasking" => Some(Box::new(image_analysis::UnsharpMasking::new())),
             "userdefinedweightsfilter" => {
@@ -956,7 +1142,11 @@ impl ToolManager {
             "exp2" => Some(Box::new(math_stat_analysis::Exp2::new())),
             "zonalstatistics" => Some(Box::new(math_stat_analysis::ZonalStatistics::new())),
             "floor" => Some(Box::new(math_stat_analysis::Floor::new())),
+            "globalautocorrelation" => {
+                Some(Box::new(math_stat_analysis::GlobalAutocorrelation::new()))
+            }
             "greaterthan" => Some(Box::new(math_stat_analysis::GreaterThan::new())),
+            "hotspotanalysis" => Some(Box::new(math_stat_analysis::HotSpotAnalysis::new())),
             "imageautocorrelation" => {
                 Some(Box::new(math_stat_analysis::ImageAutocorrelation::new()))
             }
@@ -964,6 +1154,12 @@ impl ToolManager {
             "imagecorrelationneighbourhoodanalysis" => Some(Box::new(
                 math_stat_analysis::ImageCorrelationNeighbourhoodAnalysis::new(),
             )),
+            "imageglobalautocorrelation" => {
+                Some(Box::new(math_stat_analysis::ImageGlobalAutocorrelation::new()))
+            }
+            "imagehotspotanalysis" => {
+                Some(Box::new(math_stat_analysis::ImageHotSpotAnalysis::new()))
+            }
             "imageregression" => Some(Box::new(math_stat_analysis::ImageRegression::new())),
             "increment" => Some(Box::new(math_stat_analysis::Increment::new())),
             "inplaceadd" => Some(Box::new(math_stat_analysis::InPlaceAdd::new())),
@@ -1025,8 +1221,16 @@ impl ToolManager {
             "xor" => Some(Box::new(math_stat_analysis::Xor::new())),
             "zscores" => Some(Box::new(math_stat_analysis::ZScores::new())),
 
+            // precision_agriculture
+            "cleanyielddata" => Some(Box::new(precision_agriculture::CleanYieldData::new())),
+            "managementzones" => Some(Box::new(precision_agriculture::ManagementZones::new())),
+
             // stream_network_analysis
+            "conformstreamstohydrography" => Some(Box::new(
+                stream_network_analysis::ConformStreamsToHydrography::new(),
+            )),
             "distancetooutlet" => Some(Box::new(stream_network_analysis::DistanceToOutlet::new())),
+            "drainagedensity" => Some(Box::new(stream_network_analysis::DrainageDensity::new())),
             "extractstreams" => Some(Box::new(stream_network_analysis::ExtractStreams::new())),
             "extractvalleys" => Some(Box::new(stream_network_analysis::ExtractValleys::new())),
             "farthestchannelhead" => {
@@ -1066,6 +1270,9 @@ impl ToolManager {
             "streamslopecontinuous" => Some(Box::new(
                 stream_network_analysis::StreamSlopeContinuous::new(),
             )),
+            "streamsolarexposure" => Some(Box::new(
+                stream_network_analysis::StreamSolarExposure::new(),
+            )),
             "topologicalstreamorder" => Some(Box::new(
                 stream_network_analysis::TopologicalStreamOrder::new(),
             )),
@@ -1074,10 +1281,17 @@ impl ToolManager {
             }
 
             // terrain_analysis
+            "accumulationcurvature" => Some(Box::new(terrain_analysis::AccumulationCurvature::new())),
             "aspect" => Some(Box::new(terrain_analysis::Aspect::new())),
             "averagenormalvectorangulardeviation" => Some(Box::new(
                 terrain_analysis::AverageNormalVectorAngularDeviation::new(),
             )),
+            "bathymetricpositionindex" => {
+                Some(Box::new(terrain_analysis::BathymetricPositionIndex::new()))
+            }
+            "benthicterrainclassification" => {
+                Some(Box::new(terrain_analysis::BenthicTerrainClassification::new()))
+            }
             "circularvarianceofaspect" => {
                 Some(Box::new(terrain_analysis::CircularVarianceOfAspect::new()))
             }
@@ -1085,6 +1299,7 @@ impl ToolManager {
             "contoursfromraster" => Some(Box::new(terrain_analysis::ContoursFromRaster::new())),
             "devfrommeanelev" => Some(Box::new(terrain_analysis::DevFromMeanElev::new())),
             "difffrommeanelev" => Some(Box::new(terrain_analysis::DiffFromMeanElev::new())),
+            "differencecurvature" => Some(Box::new(terrain_analysis::DifferenceCurvature::new())),
             "directionalrelief" => Some(Box::new(terrain_analysis::DirectionalRelief::new())),
             "downslopeindex" => Some(Box::new(terrain_analysis::DownslopeIndex::new())),
             "edgedensity" => Some(Box::new(terrain_analysis::EdgeDensity::new())),
@@ -1101,14 +1316,22 @@ impl ToolManager {
             "fetchanalysis" => Some(Box::new(terrain_analysis::FetchAnalysis::new())),
             "fillmissingdata" => Some(Box::new(terrain_analysis::FillMissingData::new())),
             "findridges" => Some(Box::new(terrain_analysis::FindRidges::new())),
+            "fractalterrain" => Some(Box::new(terrain_analysis::FractalTerrain::new())),
             "gaussiancurvature" => Some(Box::new(terrain_analysis::GaussianCurvature::new())),
+            "gaussianhills" => Some(Box::new(terrain_analysis::GaussianHills::new())),
             "geomorphons" => Some(Box::new(terrain_analysis::Geomorphons::new())),
+            "glacierflowlines" => Some(Box::new(terrain_analysis::GlacierFlowlines::new())),
+            "glacierhypsometry" => Some(Box::new(terrain_analysis::GlacierHypsometry::new())),
             "hillshade" => Some(Box::new(terrain_analysis::Hillshade::new())),
             "horizonangle" => Some(Box::new(terrain_analysis::HorizonAngle::new())),
+            "horizontalexcesscurvature" => Some(Box::new(
+                terrain_analysis::HorizontalExcessCurvature::new(),
+            )),
             "hypsometricanalysis" => Some(Box::new(terrain_analysis::HypsometricAnalysis::new())),
             "hypsometricallytintedhillshade" => Some(Box::new(
                 terrain_analysis::HypsometricallyTintedHillshade::new(),
             )),
+            "localdominance" => Some(Box::new(terrain_analysis::LocalDominance::new())),
             "mapoffterrainobjects" => Some(Box::new(terrain_analysis::MapOffTerrainObjects::new())),
             "maxanisotropydev" => Some(Box::new(terrain_analysis::MaxAnisotropyDev::new())),
             "maxanisotropydevsignature" => {
@@ -1155,8 +1378,10 @@ impl ToolManager {
                 Some(Box::new(terrain_analysis::NumDownslopeNeighbours::new()))
             }
             "numupslopeneighbours" => Some(Box::new(terrain_analysis::NumUpslopeNeighbours::new())),
+            "openness" => Some(Box::new(terrain_analysis::Openness::new())),
             "pennocklandformclass" => Some(Box::new(terrain_analysis::PennockLandformClass::new())),
             "percentelevrange" => Some(Box::new(terrain_analysis::PercentElevRange::new())),
+            "perlinnoiseterrain" => Some(Box::new(terrain_analysis::PerlinNoiseTerrain::new())),
             "plancurvature" => Some(Box::new(terrain_analysis::PlanCurvature::new())),
             "profilecurvature" => Some(Box::new(terrain_analysis::ProfileCurvature::new())),
             "profile" => Some(Box::new(terrain_analysis::Profile::new())),
@@ -1168,13 +1393,22 @@ impl ToolManager {
             "removeoffterrainobjects" => {
                 Some(Box::new(terrain_analysis::RemoveOffTerrainObjects::new()))
             }
+            "ringcurvature" => Some(Box::new(terrain_analysis::RingCurvature::new())),
+            "rotor" => Some(Box::new(terrain_analysis::Rotor::new())),
             "ruggednessindex" => Some(Box::new(terrain_analysis::RuggednessIndex::new())),
             // "segmentterrain" => Some(Box::new(terrain_analysis::SegmentTerrain::new())),
             "timeindaylight" => Some(Box::new(terrain_analysis::TimeInDaylight::new())),
             "sedimenttransportindex" => {
                 Some(Box::new(terrain_analysis::SedimentTransportIndex::new()))
             }
+            "shallowlandslidesusceptibility" => Some(Box::new(
+                terrain_analysis::ShallowLandslideSusceptibility::new(),
+            )),
+            "skyviewfactor" => Some(Box::new(terrain_analysis::SkyViewFactor::new())),
             "slope" => Some(Box::new(terrain_analysis::Slope::new())),
+            "slopepositionclassification" => Some(Box::new(
+                terrain_analysis::SlopePositionClassification::new(),
+            )),
             "slopevselevationplot" => Some(Box::new(terrain_analysis::SlopeVsElevationPlot::new())),
             "sphericalstddevofnormals" => {
                 Some(Box::new(terrain_analysis::SphericalStdDevOfNormals::new()))
@@ -1183,8 +1417,15 @@ impl ToolManager {
                 Some(Box::new(terrain_analysis::StandardDeviationOfSlope::new()))
             }
             "surfacearearatio" => Some(Box::new(terrain_analysis::SurfaceAreaRatio::new())),
+            "syntheticvalley" => Some(Box::new(terrain_analysis::SyntheticValley::new())),
             "tangentialcurvature" => Some(Box::new(terrain_analysis::TangentialCurvature::new())),
+            "terrainadjusteddegreedays" => {
+                Some(Box::new(terrain_analysis::TerrainAdjustedDegreeDays::new()))
+            }
             "totalcurvature" => Some(Box::new(terrain_analysis::TotalCurvature::new())),
+            "verticalexcesscurvature" => Some(Box::new(
+                terrain_analysis::VerticalExcessCurvature::new(),
+            )),
             "viewshed" => Some(Box::new(terrain_analysis::Viewshed::new())),
             "visibilityindex" => Some(Box::new(terrain_analysis::VisibilityIndex::new())),
             "wetnessindex" => Some(Box::new(terrain_analysis::WetnessIndex::new())),
@@ -1226,8 +1467,261 @@ impl ToolManager {
     }
 
     pub fn run_tool(&self, tool_name: String, args: Vec<String>) -> Result<(), Error> {
+        self.run_tool_with_progress(tool_name, args, self.progress_reporter())
+    }
+
+    /// Runs a tool, as `run_tool` does, sending its progress to `reporter` rather than to the
+    /// tool manager's progress reporter.
+    pub fn run_tool_with_progress(
+        &self,
+        tool_name: String,
+        args: Vec<String>,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let cancellable;
+        let reporter = match &self.cancellation {
+            Some(token) => {
+                cancellable = CancellableProgress::new(reporter, token.clone());
+                &cancellable as &dyn ProgressReporter
+            }
+            None => reporter,
+        };
+        let manifest_file = whitebox_common::configs::get_configs()?.run_manifest;
+        if manifest_file.trim().is_empty() {
+            return self.execute_tool(tool_name, args, reporter);
+        }
+
+        // Record the run in the manifest, including runs that end in a panic.
+        let mut entry = run_manifest::ManifestEntry::new(&tool_name, &self.working_dir, &args, chrono::Local::now());
+        let start = Instant::now();
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            self.execute_tool(tool_name.clone(), args.clone(), reporter)
+        }));
+        entry.duration_seconds = start.elapsed().as_secs_f64();
+        match &result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                entry.status = if e.kind() == ErrorKind::Interrupted {
+                    String::from("cancelled")
+                } else {
+                    String::from("failed")
+                };
+                entry.error = Some(e.to_string());
+            }
+            Err(payload) => {
+                entry.status = String::from("failed");
+                entry.error = Some(
+                    payload
+                        .downcast_ref::<String>()
+                        .cloned()
+                        .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                        .unwrap_or(String::from("The tool panicked.")),
+                );
+            }
+        }
+        entry.outputs = self.existing_outputs(&tool_name, &args);
+        if let Err(e) = run_manifest::append_entry(&resolve_path(&manifest_file, &self.working_dir), &entry) {
+            println!("Warning: the run could not be recorded in the run manifest: {}", e);
+        }
+        match result {
+            Ok(r) => r,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+
+    /// Adds the parameter values of a preset (see the presets module) to the arguments of a run
+    /// of a tool. A run that names no preset uses the tool's 'default' preset, if it has one, and
+    /// can't be stopped by an unreadable presets file, which is reported as a warning instead.
+    fn apply_preset(
+        &self,
+        tool: &dyn WhiteboxTool,
+        mut args: Vec<String>,
+        preset: &str,
+        shared_presets_file: &str,
+    ) -> Result<Vec<String>, Error> {
+        let all_presets = match presets::read_presets(shared_presets_file) {
+            Ok(p) => p,
+            Err(e) if preset.is_empty() => {
+                println!("Warning: the default preset could not be applied: {}", e);
+                return Ok(args);
+            }
+            Err(e) => return Err(e),
+        };
+        let parameters = tool.get_tool_parameters();
+        let supplied: Vec<serde_json::Value> = parse_tool_args(&parameters, &args)?
+            .into_iter()
+            .map(|(param, _)| param)
+            .collect();
+        if let Some((name, preset_args)) =
+            presets::preset_args(&all_presets, &tool.get_tool_name(), &parameters, &supplied, preset)?
+        {
+            if self.verbose {
+                println!("Applying the preset '{}': {}", name, preset_args.join(" "));
+            }
+            args.extend(preset_args);
+        }
+        Ok(args)
+    }
+
+    /// Prints, as JSON, the presets of a tool, or of all tools if `tool_name` is empty, along
+    /// with the location of the user's presets file, in which new presets are saved.
+    pub fn list_presets(&self, tool_name: String) -> Result<(), Error> {
+        let configs = whitebox_common::configs::get_configs()?;
+        let all_presets = presets::read_presets(&configs.presets_file)?;
+        let mut listed = presets::Presets::new();
+        if tool_name.trim().is_empty() {
+            listed = all_presets;
+        } else {
+            let name = match self.get_tool(&tool_name) {
+                Some(tool) => tool.get_tool_name(),
+                None => tool_name.clone(),
+            };
+            if let Some(p) = presets::tool_presets(&all_presets, &name) {
+                listed.insert(name, p.clone());
+            }
+        }
+        let user_file = presets::user_presets_file()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let j = serde_json::json!({
+            "user_file": user_file,
+            "shared_file": configs.presets_file,
+            "presets": listed,
+        });
+        println!("{}", serde_json::to_string_pretty(&j)?);
+        Ok(())
+    }
+
+    /// Returns the output files named in a tool's arguments that exist.
+    fn existing_outputs(&self, tool_name: &str, args: &[String]) -> Vec<String> {
+        let mut outputs = vec![];
+        if let Some(tool) = self.get_tool(tool_name) {
+            if let Ok(params) = parse_tool_args(&tool.get_tool_parameters(), args) {
+                for (param, value) in params {
+                    let file_name = resolve_path(&value, &self.working_dir);
+                    if param["parameter_type"].get("NewFile").is_some() && path::Path::new(&file_name).exists() {
+                        outputs.push(file_name);
+                    }
+                }
+            }
+        }
+        outputs
+    }
+
+    /// Runs a tool and prints JSON events describing the run (start, metadata, progress,
+    /// messages, and its end) to the standard output in place of the tool's text output.
+    pub fn run_tool_json(&self, tool_name: String, args: Vec<String>) -> Result<(), Error> {
+        json_events::run_json_events(self, &tool_name, &args)
+    }
+
+    /// Repeats each run recorded in a run manifest whose most recent attempt failed. The
+    /// outcomes of the repeated runs are appended to the same manifest.
+    pub fn rerun_failed(&self, manifest_file: &str) -> Result<(), Error> {
+        let manifest_file = resolve_path(manifest_file, &self.working_dir);
+        let runs = run_manifest::failed_runs(&manifest_file)?;
+        if runs.is_empty() {
+            println!("There are no failed runs in {}.", manifest_file);
+            return Ok(());
+        }
+        let mut configs = whitebox_common::configs::get_configs()?;
+        configs.run_manifest = manifest_file.clone();
+        whitebox_common::configs::set_run_configs(&configs);
+
+        let mut num_failed = 0;
+        for (i, run) in runs.iter().enumerate() {
+            println!("Re-running {} ({} of {})...", run.tool, i + 1, runs.len());
+            let tm = ToolManager::new(&run.working_directory, &self.verbose)?;
+            let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                tm.run_tool_with_progress(run.tool.clone(), run.args.clone(), self.progress_reporter())
+            }));
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    println!("{} failed: {}", run.tool, e);
+                    num_failed += 1;
+                }
+                Err(_) => num_failed += 1,
+            }
+        }
+        if num_failed > 0 {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("{} of {} re-run tools failed again; see {}.", num_failed, runs.len(), manifest_file),
+            ));
+        }
+        println!("All {} failed runs completed successfully.", runs.len());
+        Ok(())
+    }
+
+    /// Runs a tool `repetitions` times at each of a series of thread counts and prints a JSON
+    /// report of its wall, I/O, and compute times and peak memory use.
+    pub fn benchmark(&self, tool_name: String, args: Vec<String>, repetitions: usize) -> Result<(), Error> {
+        benchmark::run_benchmark(self, &tool_name, &args, repetitions)
+    }
+
+    /// Runs the built-in self tests whose name or tool contains `filter`, or all of the self
+    /// tests if `filter` is empty.
+    pub fn self_test(&self, filter: &str) -> Result<(), Error> {
+        self_test::run_self_tests(self, filter)
+    }
+
+    /// Keeps the process running as a server that accepts JSON-RPC tool invocations over TCP
+    /// connections at `address`, e.g. 127.0.0.1:9300, until a client asks it to shut down.
+    pub fn serve(&self, address: &str) -> Result<(), Error> {
+        server::run_server(&self.working_dir, address)
+    }
+
+    fn execute_tool(
+        &self,
+        tool_name: String,
+        mut args: Vec<String>,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let preset = presets::take_preset_arg(&mut args);
         match self.get_tool(tool_name.as_ref()) {
-            Some(tool) => return tool.run(args, &self.working_dir, self.verbose),
+            Some(tool) => {
+                let configs = whitebox_common::configs::get_configs()?;
+                args = self.apply_preset(&*tool, args, &preset, &configs.presets_file)?;
+                if configs.no_overwrite {
+                    check_existing_outputs(&tool.get_tool_parameters(), &args, &self.working_dir)?;
+                }
+                // Each run has its own rayon pool, sized by the settings of that run, rather than
+                // sharing the global pool, which can only be sized once per process.
+                let pool = ThreadPool::new(&configs);
+                let rayon_pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(pool.num_threads())
+                    .build()
+                    .map_err(|e| Error::new(ErrorKind::Other, format!("The thread pool could not be created: {}", e)))?;
+                let (result, threads_used) = rayon_pool.install(|| {
+                    record_threads_used(|| tool.run(args.clone(), &self.working_dir, self.verbose, reporter))
+                });
+                result?;
+                if self.verbose {
+                    // reported for cluster schedulers that allocate a job a specific number of cores
+                    let pinning = match pool.numa_node_count() {
+                        0 => String::new(),
+                        n => format!(", pinned across {} NUMA nodes", n),
+                    };
+                    println!(
+                        "Threads used: {} of {} available processors{}",
+                        threads_used.max(1),
+                        available_threads(),
+                        pinning
+                    );
+                }
+                if configs.provenance {
+                    // A failure to record provenance shouldn't cause the run to fail.
+                    if let Err(e) = provenance::write_provenance(
+                        &tool.get_tool_name(),
+                        &tool.get_tool_parameters(),
+                        &args,
+                        &self.working_dir,
+                    ) {
+                        println!("Warning: the provenance sidecar could not be written: {}", e);
+                    }
+                }
+                return Ok(());
+            }
             None => {
                 // Check the 'plugins' folder to see if the tool is in the Enterprise plugins.
                 // if yes, then run it.
@@ -1259,9 +1753,15 @@ impl ToolManager {
                     );
                     let mut subcommand = vec!["run".to_string()];
                     subcommand.extend_from_slice(&args);
+                    // Plugins read their settings themselves, and so the settings of this run are
+                    // passed to them as environment variables.
+                    let configs = whitebox_common::configs::get_configs()?;
                     let mut child = Command::new(exe)
                         .arg("run")
                         .args(&args2)
+                        .env("WBT_MAX_PROCS", configs.max_procs.to_string())
+                        .env("WBT_NUMA_PINNING", configs.numa_pinning.to_string())
+                        .env("WBT_COMPUTE", &configs.compute)
                         .spawn()
                         .expect("failed to execute process");
 
@@ -1269,7 +1769,8 @@ impl ToolManager {
                         .expect("failed to wait on child");
                     
                     if !ecode.success() {
-                        println!("Failure to run plugin subprocess.");
+                        let cause = ecode.code().and_then(ErrorCause::from_exit_code).unwrap_or(ErrorCause::Other);
+                        return Err(cause.error(format!("Failure to run plugin subprocess ({}).", ecode)));
                     }
                 } else {
                     // We couldn't find an executable file for the tool, but still check to see if it's 
@@ -1335,15 +1836,11 @@ impl ToolManager {
                         "yieldnormalization"
                     ];
                     if plugin_names.contains(&tool_name.to_lowercase().as_ref()) {
-                        return Err(Error::new(
-                            ErrorKind::NotFound,
+                        return Err(ErrorCause::License.error(
                             format!("Invalid license: \nThis tool is part of a Whitebox extension product \nand there is a missing license. Please contact \nWhitebox Geospatial Inc. (support@whiteboxgeo.com) to obtain \na valid license key."),
                         ))
                     } else {
-                        return Err(Error::new(
-                            ErrorKind::NotFound,
-                            format!("Unrecognized tool name {}.", tool_name),
-                        ))
+                        return Err(ErrorCause::InvalidParameter.error(format!("Unrecognized tool name {}.", tool_name)))
                     }
                 }
                 return Ok(())
@@ -1428,10 +1925,7 @@ Example usage:
                         println!("{}", s);
 
                     } else {
-                        return Err(Error::new(
-                            ErrorKind::NotFound,
-                            format!("Unrecognized tool name {}.", tool_name),
-                        ))
+                        return Err(ErrorCause::InvalidParameter.error(format!("Unrecognized tool name {}.", tool_name)))
                     }
                 }
             }
@@ -1455,10 +1949,7 @@ Example usage:
                     let plugin_data = plugin_list.get(&tool_name.to_lowercase()).expect(&format!("Unrecognized plugin name {}.", tool_name));
                     println!("{}", plugin_data["license"].as_str().expect("Cannot locate plugin tool license."));
                 } else {
-                    return Err(Error::new(
-                        ErrorKind::NotFound,
-                        format!("Unrecognized tool name {}.", tool_name),
-                    ))
+                    return Err(ErrorCause::InvalidParameter.error(format!("Unrecognized tool name {}.", tool_name)))
                 }
             }
         }
@@ -1476,10 +1967,7 @@ Example usage:
                     // println!("{:?}", plugin_data);
                     println!("{}", plugin_data);
                 } else {
-                    return Err(Error::new(
-                        ErrorKind::NotFound,
-                        format!("Unrecognized tool name {}.", tool_name),
-                    ))
+                    return Err(ErrorCause::InvalidParameter.error(format!("Unrecognized tool name {}.", tool_name)))
                 }
             }
         }
@@ -1497,10 +1985,7 @@ Example usage:
                         let toolbox = plugin_data["toolbox"].as_str().unwrap_or("Toolbox name not found.");
                         println!("{}", toolbox);
                     } else {
-                        return Err(Error::new(
-                            ErrorKind::NotFound,
-                            format!("Unrecognized tool name {}.", tool_name),
-                        ))
+                        return Err(ErrorCause::InvalidParameter.error(format!("Unrecognized tool name {}.", tool_name)))
                     }
                     // for (_key, plugin_data) in &plugin_list {
                     //     let tool = plugin_data["tool_name"].as_str().unwrap_or("Tool name not found.");
@@ -1621,10 +2106,7 @@ Example usage:
                     let plugin_data = plugin_list.get(&tool_name.to_lowercase()).expect(&format!("Unrecognized plugin name {}.", tool_name));
                     license = plugin_data["license"].as_str().expect("Cannot locate plugin tool license.").to_lowercase();
                 } else {
-                    return Err(Error::new(
-                        ErrorKind::NotFound,
-                        format!("Unrecognized tool name {}.", tool_name),
-                    ))
+                    return Err(ErrorCause::InvalidParameter.error(format!("Unrecognized tool name {}.", tool_name)))
                 }
                 // let license = self.tool_license(tool_name.clone()).to_lowercase();
                 if !license.contains("proprietary") {
@@ -1632,10 +2114,7 @@ Example usage:
                 } else {
                     println!("Source code is unavailable due to proprietary license.");
                 }
-                return Err(Error::new(
-                    ErrorKind::NotFound,
-                    format!("Unrecognized tool name {}.", tool_name),
-                ))
+                return Err(ErrorCause::InvalidParameter.error(format!("Unrecognized tool name {}.", tool_name)))
             }
         }
 
@@ -1643,7 +2122,8 @@ Example usage:
     }
 }
 
-pub trait WhiteboxTool {
+/// A tool. Tools are run within the rayon pool of their run, and so must be `Send` and `Sync`.
+pub trait WhiteboxTool: Send + Sync {
     fn get_tool_name(&self) -> String;
     fn get_tool_description(&self) -> String;
     fn get_tool_parameters(&self) -> String;
@@ -1655,6 +2135,7 @@ pub trait WhiteboxTool {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error>;
 }
 
@@ -1714,6 +2195,618 @@ fn get_name_and_description<'a>(wt: Box<dyn WhiteboxTool + 'a>) -> (String, Stri
     (wt.get_tool_name(), wt.get_tool_description())
 }
 
+/// The value of a tool argument, of the type given by its parameter's `ParameterType`.
+#[derive(Clone, Debug, PartialEq)]
+enum ArgValue {
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    List(Vec<String>),
+}
+
+/// The arguments of a tool run, parsed and validated against the tool's parameters by
+/// `parse_args`. Values are looked up by any of a parameter's flags, with or without the leading
+/// dashes, e.g. `args.get_string("input")` or `args.get_string("-i")`. Optional parameters that
+/// were not supplied have their default value, if they have one.
+#[derive(Debug, Default)]
+struct ParsedArgs {
+    values: HashMap<String, ArgValue>,
+    flags: HashMap<String, String>,
+    // the values of flags that aren't parameters, keyed by their flag without dashes
+    silent: HashMap<String, String>,
+}
+
+impl ParsedArgs {
+    /// Returns the key of the parameter with a flag, which is its last (i.e. long) flag.
+    fn key(&self, flag: &str) -> String {
+        let flag = normalize_flag(flag);
+        self.flags.get(&flag).cloned().unwrap_or(flag)
+    }
+
+    fn get(&self, flag: &str) -> Option<&ArgValue> {
+        self.values.get(&self.key(flag))
+    }
+
+    fn get_string(&self, flag: &str) -> Option<String> {
+        match self.get(flag)? {
+            ArgValue::Text(s) => Some(s.clone()),
+            ArgValue::Boolean(b) => Some(b.to_string()),
+            ArgValue::Integer(i) => Some(i.to_string()),
+            ArgValue::Float(f) => Some(f.to_string()),
+            ArgValue::List(l) => Some(l.join(";")),
+        }
+    }
+
+    /// Returns the value of a Boolean argument, which is false if it was not supplied.
+    fn get_bool(&self, flag: &str) -> bool {
+        self.get(flag) == Some(&ArgValue::Boolean(true))
+    }
+
+    fn get_f64(&self, flag: &str) -> Option<f64> {
+        match self.get(flag)? {
+            ArgValue::Float(f) => Some(*f),
+            ArgValue::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    fn get_i64(&self, flag: &str) -> Option<i64> {
+        match self.get(flag)? {
+            ArgValue::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an Integer argument, or an error if it is negative.
+    fn get_usize(&self, flag: &str) -> Result<Option<usize>, Error> {
+        match self.get_i64(flag) {
+            Some(i) if i < 0 => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The --{} parameter must not be negative.", self.key(flag)),
+            )),
+            Some(i) => Ok(Some(i as usize)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the value of a silent flag, i.e. one that the command-line interface accepts but
+    /// that isn't one of the tool's parameters, e.g. the `--filter` flag of the filter tools,
+    /// which sets both `--filterx` and `--filtery`. The value is unchecked.
+    fn get_silent(&self, flag: &str) -> Option<String> {
+        self.silent.get(&normalize_flag(flag)).cloned()
+    }
+
+    /// Returns the value of a silent flag as a number, or an error if it is not a number. Numbers
+    /// may be locale-formatted, as with `parse_number`.
+    fn get_silent_f64(&self, flag: &str) -> Result<Option<f64>, Error> {
+        match self.get_silent(flag) {
+            Some(value) => match parse_number(&value) {
+                Some(v) => Ok(Some(v)),
+                None => Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Error parsing --{}: '{}' is not a number.", normalize_flag(flag), value),
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the items of a FileList or StringList argument, which is empty if it was not supplied.
+    fn get_list(&self, flag: &str) -> Vec<String> {
+        match self.get(flag) {
+            Some(ArgValue::List(l)) => l.clone(),
+            Some(ArgValue::Text(s)) => vec![s.clone()],
+            _ => vec![],
+        }
+    }
+
+    /// Returns the items of a StringList argument as numbers, e.g. class values or weights, or an
+    /// error if any item is not a number. Items may be locale-formatted, as with `parse_number`.
+    fn get_f64_list(&self, flag: &str) -> Result<Vec<f64>, Error> {
+        self.get_list(flag)
+            .iter()
+            .map(|item| {
+                parse_number(item).ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Error parsing --{}: '{}' is not a number.", self.key(flag), item),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+fn normalize_flag(flag: &str) -> String {
+    flag.trim().trim_start_matches('-').to_lowercase()
+}
+
+/// Removes the quotes around a whole argument value, e.g. `'my dem.tif'`. Quotes within a value,
+/// such as the apostrophe of `O'Brien.tif` or those around the items of a list, are kept.
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if value.len() >= 2
+            && value.starts_with(quote)
+            && value.ends_with(quote)
+            && !value[1..value.len() - 1].contains(quote)
+        {
+            return &value[1..value.len() - 1];
+        }
+    }
+    value
+}
+
+/// Parses a number that may be written in the convention of the user's locale, e.g. `1234.5`,
+/// `1234,5`, `1 234,5`, `1.234,5`, `1,234.5`, or `1'234.5`. When a number contains both commas and
+/// points, the last of them is the decimal separator, and a number with a single comma (or point)
+/// takes it to be the decimal separator, such that `2,5` is 2.5. A single comma followed by exactly
+/// three digits, e.g. `1,000`, may be either separator and is rejected (see
+/// `is_ambiguous_comma`). Separators that are repeated, e.g. `1.234.567`, group thousands and must
+/// be followed by groups of three digits. Spaces (including non-breaking and thin spaces) and
+/// apostrophes between digits are thousands separators.
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim().replace('\u{2212}', "-");
+    if let Ok(v) = value.parse::<f64>() {
+        return Some(v);
+    }
+    if is_ambiguous_comma(&value) {
+        return None;
+    }
+    // split off the sign and exponent, which need no conversion
+    let (sign, rest) = match value.strip_prefix(['-', '+']) {
+        Some(rest) => (&value[..1], rest),
+        None => ("", value.as_str()),
+    };
+    let (mantissa, exponent) = match rest.find(['e', 'E']) {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, ""),
+    };
+    let mut digits = String::new();
+    let chars: Vec<char> = mantissa.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        let between_digits = i > 0
+            && i + 1 < chars.len()
+            && chars[i - 1].is_ascii_digit()
+            && chars[i + 1].is_ascii_digit();
+        match c {
+            ' ' | '\u{a0}' | '\u{202f}' | '\'' | '\u{2019}' if between_digits => digits.push(' '),
+            '0'..='9' | '.' | ',' => digits.push(c),
+            _ => return None,
+        }
+    }
+    let decimal = match (digits.rfind('.'), digits.rfind(',')) {
+        (Some(p), Some(c)) => Some(if p > c { '.' } else { ',' }),
+        (Some(_), None) if digits.matches('.').count() == 1 => Some('.'),
+        (None, Some(_)) if digits.matches(',').count() == 1 => Some(','),
+        _ => None,
+    };
+    let (integer, fraction) = match decimal {
+        Some(d) => {
+            let i = digits.rfind(d).unwrap();
+            (&digits[..i], &digits[i + 1..])
+        }
+        None => (digits.as_str(), ""),
+    };
+    if let Some(d) = decimal {
+        if integer.contains(d) {
+            return None;
+        }
+    }
+    // the integer part is either ungrouped or a run of groups of three digits after the first
+    let separators: Vec<char> = integer.chars().filter(|c| !c.is_ascii_digit()).collect();
+    if !separators.is_empty() {
+        if separators.iter().any(|&c| c != separators[0]) {
+            return None;
+        }
+        let groups: Vec<&str> = integer.split(separators[0]).collect();
+        if groups[0].is_empty()
+            || groups[0].len() > 3
+            || groups[1..].iter().any(|g| g.len() != 3)
+        {
+            return None;
+        }
+    }
+    if fraction.contains(|c: char| !c.is_ascii_digit()) {
+        return None;
+    }
+    let integer: String = integer.chars().filter(|c| c.is_ascii_digit()).collect();
+    format!("{}{}.{}{}", sign, integer, fraction, exponent)
+        .trim_end_matches('.')
+        .parse::<f64>()
+        .ok()
+}
+
+/// Returns true if a number is written with a single comma followed by exactly three digits, e.g.
+/// `1,000` or `-12,500e3`, which is 1000 where the comma groups thousands but 1.0 where it is the
+/// decimal separator. A leading zero, as in `0,125`, can't begin a group of thousands.
+fn is_ambiguous_comma(value: &str) -> bool {
+    let value = value.trim().trim_start_matches(['-', '+', '\u{2212}']);
+    let mantissa = value.split(['e', 'E']).next().unwrap_or("");
+    match mantissa.split_once(',') {
+        Some((integer, fraction)) => {
+            (1..=3).contains(&integer.len())
+                && !integer.starts_with('0')
+                && integer.chars().all(|c| c.is_ascii_digit())
+                && fraction.len() == 3
+                && fraction.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+/// Splits the value of a list argument into its items. Items are separated by semicolons or, if
+/// the value contains no semicolons, by commas. An item may be quoted, e.g. `"dem, 2020.tif"`, or
+/// escape a separator or quote with a backslash (`\,`), to include separators and quotes; other
+/// backslashes, such as those of Windows paths, are kept. Items are trimmed, and empty items
+/// are dropped.
+fn split_list(value: &str) -> Vec<String> {
+    match split_on(value, ';') {
+        (items, true) => items,
+        _ => split_on(value, ',').0,
+    }
+}
+
+/// Splits a list on a delimiter, returning the items and whether the delimiter was found.
+fn split_on(value: &str, delimiter: char) -> (Vec<String>, bool) {
+    let mut items = vec![];
+    let mut found = false;
+    let mut item = String::new();
+    // the quote of an item that is being read, and whether the item was quoted
+    let mut quote: Option<char> = None;
+    let mut quoted = false;
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some(&n) if n == delimiter || n == '"' || n == '\'' || n == '\\') => {
+                item.push(chars.next().unwrap());
+            }
+            _ if quote == Some(c) => quote = None,
+            _ if quote.is_some() => item.push(c),
+            // quotes only open an item, such that apostrophes within items are kept
+            '"' | '\'' if !quoted && item.trim().is_empty() => {
+                item.clear();
+                quote = Some(c);
+                quoted = true;
+            }
+            _ if c == delimiter => {
+                found = true;
+                let s = if quoted { item.clone() } else { item.trim().to_string() };
+                if !s.is_empty() {
+                    items.push(s);
+                }
+                item.clear();
+                quoted = false;
+            }
+            // text following the closing quote of an item, other than spaces, is kept
+            _ if quoted && c.is_whitespace() => {}
+            _ => item.push(c),
+        }
+    }
+    let s = if quoted { item } else { item.trim().to_string() };
+    if !s.is_empty() {
+        items.push(s);
+    }
+    (items, found)
+}
+
+/// Parses the arguments of a tool run against the tool's parameters, replacing the argument
+/// parsing loop that tools otherwise write for themselves. Values may be given as `--flag=value` or
+/// `--flag value`, and a Boolean flag given without a value is true. Each value is checked against
+/// the type of its parameter (e.g. Integer, Float, or one of the values of an OptionList), required
+/// parameters must be supplied, and optional parameters take their default values. Arguments that
+/// match none of the parameters, e.g. -v, are kept as silent flags (see `ParsedArgs::get_silent`).
+///
+/// Numbers may be locale-formatted (see `parse_number`), and list values follow the quoting and
+/// escaping rules of `split_list`. Quotes around a whole value are removed, but quotes within it
+/// are kept.
+fn parse_args(parameters: &[ToolParameter], args: &[String]) -> Result<ParsedArgs, Error> {
+    if args.len() == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Tool run with no parameters.",
+        ));
+    }
+    let mut parsed = ParsedArgs::default();
+    for param in parameters {
+        let key = param.flags.last().map(|f| normalize_flag(f)).unwrap_or_default();
+        for flag in &param.flags {
+            parsed.flags.insert(normalize_flag(flag), key.clone());
+        }
+    }
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = unquote(&args[i]);
+        let vec = arg.splitn(2, '=').collect::<Vec<&str>>();
+        i += 1;
+        if !vec[0].starts_with('-') {
+            continue;
+        }
+        let key = match parsed.flags.get(&normalize_flag(vec[0])) {
+            Some(key) => key.clone(),
+            None => {
+                let value = if vec.len() > 1 {
+                    unquote(vec[1]).to_string()
+                } else if i < args.len() && !is_flag(&args[i]) {
+                    unquote(&args[i]).to_string()
+                } else {
+                    String::new()
+                };
+                parsed.silent.insert(normalize_flag(vec[0]), value);
+                continue;
+            }
+        };
+        let param = parameters
+            .iter()
+            .find(|p| p.flags.last().map(|f| normalize_flag(f)) == Some(key.clone()))
+            .expect("Every flag belongs to a parameter.");
+        let value = if vec.len() > 1 {
+            unquote(vec[1]).to_string()
+        } else if let ParameterType::Boolean = param.parameter_type {
+            "true".to_string()
+        } else if i < args.len() && !is_flag(&args[i]) {
+            i += 1;
+            unquote(&args[i - 1]).to_string()
+        } else {
+            // rather than taking the next flag as the value
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("The --{} parameter ({}) requires a value.", key, param.name),
+            ));
+        };
+        let value = parse_arg_value(&key, &param.parameter_type, &value)?;
+        parsed.values.insert(key, value);
+    }
+
+    for param in parameters {
+        let key = param.flags.last().map(|f| normalize_flag(f)).unwrap_or_default();
+        if parsed.values.contains_key(&key) {
+            continue;
+        }
+        match &param.default_value {
+            Some(default) if !default.trim().is_empty() => {
+                let value = parse_arg_value(&key, &param.parameter_type, default)?;
+                parsed.values.insert(key, value);
+            }
+            _ => {
+                if matches!(param.parameter_type, ParameterType::Boolean) {
+                    // an absent flag is off
+                    parsed.values.insert(key, ArgValue::Boolean(false));
+                } else if !param.optional {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("The --{} parameter ({}) is required.", key, param.name),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+/// Returns true if an argument is a flag, e.g. `--dem`, rather than a value, including a negative
+/// number such as `-5`.
+fn is_flag(arg: &str) -> bool {
+    let arg = unquote(arg);
+    arg.starts_with('-') && parse_number(arg.split('=').next().unwrap_or("")).is_none()
+}
+
+/// Parses the value of an argument as the type of its parameter.
+fn parse_arg_value(key: &str, parameter_type: &ParameterType, value: &str) -> Result<ArgValue, Error> {
+    let value = value.trim();
+    let invalid = |expected: &str| {
+        if is_ambiguous_comma(value) {
+            return Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Error parsing --{}: '{}' is ambiguous, as its comma may group thousands or be the decimal separator; write it without the comma (e.g. 1000) or with a point (e.g. 1.000).",
+                    key, value
+                ),
+            );
+        }
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Error parsing --{}: '{}' is not {}.", key, value, expected),
+        )
+    };
+    match parameter_type {
+        ParameterType::Boolean => match value.to_lowercase().as_str() {
+            "" | "true" | "t" | "yes" | "1" => Ok(ArgValue::Boolean(true)),
+            "false" | "f" | "no" | "0" => Ok(ArgValue::Boolean(false)),
+            _ => Err(invalid("true or false")),
+        },
+        ParameterType::Integer => {
+            // integers are accepted in floating-point form, e.g. 5.0, as tools have always done
+            let v = parse_number(value).ok_or_else(|| invalid("an integer"))?;
+            if v.fract() != 0f64 || !v.is_finite() {
+                return Err(invalid("an integer"));
+            }
+            Ok(ArgValue::Integer(v as i64))
+        }
+        ParameterType::Float => Ok(ArgValue::Float(
+            parse_number(value).ok_or_else(|| invalid("a number"))?,
+        )),
+        ParameterType::ExistingFileOrFloat(_) => Ok(match parse_number(value) {
+            Some(v) => ArgValue::Float(v),
+            None => ArgValue::Text(value.to_string()),
+        }),
+        ParameterType::FileList(_) | ParameterType::StringList => Ok(ArgValue::List(split_list(value))),
+        ParameterType::OptionList(options) => match match_option(options, value) {
+            Some(option) => Ok(ArgValue::Text(option.clone())),
+            None => Err(invalid(&format!("one of '{}'", options.join("', '")))),
+        },
+        _ => Ok(ArgValue::Text(value.to_string())),
+    }
+}
+
+/// Finds the option of an `OptionList` parameter that a value refers to. Besides the option itself
+/// (in any case), the forms accepted by the tools before their arguments were parsed here are
+/// allowed, as long as they refer to a single option: an abbreviation (`h` for 'horizontal'), a
+/// plural or other extension (`Bishops` for 'Bishop') and the initials of a multi-word option
+/// (`sca`, or 'specific catchment area', for 'Specific Contributing Area').
+fn match_option<'a>(options: &'a [String], value: &str) -> Option<&'a String> {
+    let value = value.to_lowercase();
+    if let Some(option) = options.iter().find(|o| o.to_lowercase() == value) {
+        return Some(option);
+    }
+    if value.is_empty() {
+        return None;
+    }
+    let initials = |s: &str| s.split_whitespace().filter_map(|w| w.chars().next()).collect::<String>();
+    let value_initials = if value.contains(char::is_whitespace) { initials(&value) } else { value.clone() };
+    let matches = options
+        .iter()
+        .filter(|o| {
+            let option = o.to_lowercase();
+            option.starts_with(&value)
+                || value.starts_with(&option)
+                || (option.contains(char::is_whitespace) && initials(&option) == value_initials)
+        })
+        .collect::<Vec<_>>();
+    if matches.len() == 1 {
+        Some(matches[0])
+    } else {
+        None
+    }
+}
+
+/// Matches the arguments of a tool run to the tool's parameters (as returned by
+/// `get_tool_parameters`), returning the description of each parameter that was supplied along
+/// with its value. Flags supplied without a value, i.e. Boolean parameters, have the value "true".
+fn parse_tool_args(parameters: &str, args: &[String]) -> Result<Vec<(serde_json::Value, String)>, Error> {
+    let parameters: serde_json::Value = serde_json::from_str(parameters)?;
+    let params = match parameters["parameters"].as_array() {
+        Some(p) => p.clone(),
+        None => return Ok(vec![]),
+    };
+    let mut ret = vec![];
+    for i in 0..args.len() {
+        let arg = unquote(&args[i]);
+        let vec = arg.splitn(2, '=').collect::<Vec<&str>>();
+        let flag = vec[0].to_lowercase().replace("--", "-");
+        let param = params.iter().find(|p| {
+            p["flags"].as_array().map_or(false, |flags| {
+                flags.iter().any(|f| f.as_str().map_or(false, |f| f.to_lowercase().replace("--", "-") == flag))
+            })
+        });
+        if let Some(param) = param {
+            let value = if vec.len() > 1 {
+                unquote(vec[1]).to_string()
+            } else if i + 1 < args.len() && (!args[i + 1].starts_with('-') || args[i + 1].parse::<f64>().is_ok()) {
+                unquote(&args[i + 1]).to_string()
+            } else {
+                "true".to_string()
+            };
+            ret.push((param.clone(), value));
+        }
+    }
+    Ok(ret)
+}
+
+/// Returns an error if any output file named in a tool's arguments already exists. This is used
+/// with the `no_overwrite` setting so that a run fails before processing begins, rather than
+/// when its outputs are written.
+fn check_existing_outputs(parameters: &str, args: &[String], working_directory: &str) -> Result<(), Error> {
+    for (param, value) in parse_tool_args(parameters, args)? {
+        if param["parameter_type"].get("NewFile").is_none() {
+            continue;
+        }
+        let file_name = resolve_path(&value, working_directory);
+        if !file_name.is_empty() && path::Path::new(&file_name).exists() {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("The output file {} already exists and the no_overwrite option is set.", file_name),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reads a secondary raster input of a multi-input tool and checks it against the grid of the primary
+/// input. If the grids differ and `align` is true (`--align=auto`), the raster is resampled and cropped
+/// to the primary grid, using bilinear interpolation for `continuous` floating-point data and
+/// nearest-neighbour interpolation otherwise, and a warning is issued. If `align` is false, inputs with
+/// differing numbers of rows or columns are an error. A warning is also issued if the inputs have
+/// different coordinate reference systems.
+fn read_secondary_raster(
+    file_name: &str,
+    primary: &Raster,
+    align: bool,
+    continuous: bool,
+    verbose: bool,
+) -> Result<Raster, Error> {
+    read_secondary_raster_on_grid(
+        file_name,
+        &primary.configs,
+        &primary.get_short_filename(),
+        align,
+        continuous,
+        verbose,
+    )
+}
+
+/// As `read_secondary_raster`, for a primary input that is described only by its configs and short
+/// file name, e.g. a `MappedRaster`.
+fn read_secondary_raster_on_grid(
+    file_name: &str,
+    primary_configs: &RasterConfigs,
+    primary_name: &str,
+    align: bool,
+    continuous: bool,
+    verbose: bool,
+) -> Result<Raster, Error> {
+    let input = Raster::new(file_name, "r")?;
+    if verbose {
+        if let Some(warning) = input.configs.crs_mismatch_warning(&input.get_short_filename(), primary_configs, primary_name) {
+            println!("{}", warning);
+        }
+    }
+    if align && !input.is_aligned_with(primary_configs) {
+        let bilinear = continuous
+            && input.configs.data_type.is_float()
+            && input.configs.photometric_interp != PhotometricInterpretation::Categorical;
+        if verbose {
+            println!(
+                "Warning: {} does not share the grid of {} and will be resampled to it using {} interpolation.",
+                input.get_short_filename(),
+                primary_name,
+                if bilinear { "bilinear" } else { "nearest-neighbour" }
+            );
+        }
+        return Ok(input.resample_to_grid(primary_configs, bilinear));
+    }
+    if input.configs.rows != primary_configs.rows || input.configs.columns != primary_configs.columns {
+        return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent. Use --align=auto to resample secondary inputs to the grid of the primary input."));
+    }
+    Ok(input)
+}
+
+/// Warns if two inputs of a multi-input tool have different coordinate reference systems. Inputs
+/// are otherwise only checked for sharing a grid, and rasters of different CRSs can share the
+/// numbers of rows and columns.
+fn warn_if_crs_differs(primary: &Raster, secondary: &Raster, verbose: bool) {
+    if verbose {
+        if let Some(warning) = secondary.configs.crs_mismatch_warning(
+            &secondary.get_short_filename(),
+            &primary.configs,
+            &primary.get_short_filename(),
+        ) {
+            println!("{}", warning);
+        }
+    }
+}
+
+/// Returns an error unless two in-memory rasters share the same grid. This is the check made by
+/// the `execute` functions of tools, which, unlike their `run` methods, do not resample inputs.
+fn check_same_grid(primary: &Raster, secondary: &Raster) -> Result<(), Error> {
+    if !secondary.is_aligned_with(&primary.configs) {
+        return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input rasters must have the same number of rows and columns and spatial extent."));
+    }
+    Ok(())
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct ToolParameter {
     name: String,
@@ -1783,3 +2876,334 @@ enum AttributeType {
     Boolean,
     Date,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{normalize_flag, parse_args, parse_number, split_list, ArgValue, CancellableProgress, CancellationToken, ConsoleProgress, ParameterFileType, ParameterType, ToolManager, ToolParameter};
+    use serde_json::Value;
+    use std::collections::HashSet;
+    use std::fs;
+    use std::io::ErrorKind;
+
+    fn parameter(flags: &[&str], parameter_type: ParameterType, default_value: Option<&str>, optional: bool) -> ToolParameter {
+        ToolParameter {
+            name: flags[flags.len() - 1].to_string(),
+            flags: flags.iter().map(|f| f.to_string()).collect(),
+            description: String::new(),
+            parameter_type,
+            default_value: default_value.map(|v| v.to_string()),
+            optional,
+        }
+    }
+
+    fn parameters() -> Vec<ToolParameter> {
+        vec![
+            parameter(&["-i", "--inputs"], ParameterType::FileList(ParameterFileType::Raster), None, false),
+            parameter(&["-o", "--output"], ParameterType::NewFile(ParameterFileType::Raster), None, false),
+            parameter(&["--size"], ParameterType::Integer, Some("11"), true),
+            parameter(&["--sigma"], ParameterType::Float, None, true),
+            parameter(&["--method"], ParameterType::OptionList(vec!["Mean".to_string(), "Median".to_string()]), Some("Mean"), true),
+            parameter(&["--clip"], ParameterType::Boolean, Some("false"), true),
+        ]
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = parse_args(
+            &parameters(),
+            &args(&["-i='a.tif;b.tif'", "--output", "out.tif", "--SIZE=5.0", "--method=median", "--clip", "-v"]),
+        )
+        .unwrap();
+        assert_eq!(parsed.get_list("inputs"), vec!["a.tif", "b.tif"]);
+        assert_eq!(parsed.get_list("-i"), vec!["a.tif", "b.tif"]);
+        assert_eq!(parsed.get_string("o"), Some("out.tif".to_string()));
+        assert_eq!(parsed.get_usize("size").unwrap(), Some(5));
+        assert_eq!(parsed.get_f64("sigma"), None);
+        assert_eq!(parsed.get_string("method"), Some("Median".to_string()));
+        assert!(parsed.get_bool("clip"));
+    }
+
+    #[test]
+    fn test_parse_args_defaults() {
+        let parsed = parse_args(&parameters(), &args(&["-i=a.tif,b.tif", "-o=out.tif", "--clip=false"])).unwrap();
+        assert_eq!(parsed.get("size"), Some(&ArgValue::Integer(11)));
+        assert_eq!(parsed.get_string("method"), Some("Mean".to_string()));
+        assert!(!parsed.get_bool("clip"));
+        assert_eq!(parsed.get_list("inputs").len(), 2);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(&parameters(), &[]).is_err());
+        // missing required parameter
+        assert!(parse_args(&parameters(), &args(&["-i=a.tif"])).is_err());
+        // invalid values
+        assert!(parse_args(&parameters(), &args(&["-i=a.tif", "-o=out.tif", "--size=5.5"])).is_err());
+        assert!(parse_args(&parameters(), &args(&["-i=a.tif", "-o=out.tif", "--sigma=abc"])).is_err());
+        assert!(parse_args(&parameters(), &args(&["-i=a.tif", "-o=out.tif", "--method=mode"])).is_err());
+        assert!(parse_args(&parameters(), &args(&["-i=a.tif", "-o=out.tif", "--size=-3"]))
+            .unwrap()
+            .get_usize("size")
+            .is_err());
+        // a flag without its value doesn't take the next flag as its value
+        let e = parse_args(&parameters(), &args(&["-i=a.tif", "--output", "--clip"])).unwrap_err();
+        assert!(e.to_string().contains("--output"), "{}", e);
+        assert!(parse_args(&parameters(), &args(&["-i=a.tif", "-o=out.tif", "--sigma"])).is_err());
+        // an ambiguous number is rejected, with the reason
+        let e = parse_args(&parameters(), &args(&["-i=a.tif", "-o=out.tif", "--sigma=1,000"])).unwrap_err();
+        assert!(e.to_string().contains("ambiguous"), "{}", e);
+        let parsed = parse_args(&parameters(), &args(&["-i=a.tif", "-o=out.tif", "--sigma", "-2,5"])).unwrap();
+        assert_eq!(parsed.get_f64("sigma"), Some(-2.5));
+    }
+
+    #[test]
+    fn test_parse_args_option_aliases() {
+        let option = |options: &[&str], value: &str| {
+            let parameters = vec![parameter(
+                &["--option"],
+                ParameterType::OptionList(options.iter().map(|o| o.to_string()).collect()),
+                None,
+                false,
+            )];
+            parse_args(&parameters, &args(&[&format!("--option={}", value)]))
+                .ok()
+                .and_then(|p| p.get_string("option"))
+        };
+        let accum = ["Cells", "Specific Contributing Area", "Catchment Area"];
+        assert_eq!(option(&accum, "sca"), Some("Specific Contributing Area".to_string()));
+        assert_eq!(option(&accum, "specific catchment area"), Some("Specific Contributing Area".to_string()));
+        assert_eq!(option(&accum, "catchment"), Some("Catchment Area".to_string()));
+        // 'c' could be 'Cells' or 'Catchment Area'
+        assert_eq!(option(&accum, "c"), None);
+        let direction = ["vertical", "horizontal", "both"];
+        assert_eq!(option(&direction, "h"), Some("horizontal".to_string()));
+        assert_eq!(option(&direction, "B"), Some("both".to_string()));
+        assert_eq!(option(&direction, "diagonal"), None);
+        assert_eq!(option(&["Rook", "King", "Bishop"], "Bishops"), Some("Bishop".to_string()));
+        assert_eq!(option(&["vertical", "horizontal", "45", "135"], "1"), Some("135".to_string()));
+    }
+
+    #[test]
+    fn test_parse_args_absent_booleans() {
+        // a required Boolean without a default is off when its flag isn't given
+        let parameters = vec![
+            parameter(&["-i", "--input"], ParameterType::ExistingFile(ParameterFileType::Raster), None, false),
+            parameter(&["--zero_back"], ParameterType::Boolean, None, false),
+        ];
+        let parsed = parse_args(&parameters, &args(&["-i=in.tif"])).unwrap();
+        assert_eq!(parsed.get("zero_back"), Some(&ArgValue::Boolean(false)));
+        assert!(parse_args(&parameters, &args(&["-i=in.tif", "--zero_back"])).unwrap().get_bool("zero_back"));
+    }
+
+    #[test]
+    fn test_parse_args_silent_flags() {
+        let parsed = parse_args(
+            &parameters(),
+            &args(&["-i=a.tif", "-o=out.tif", "--filter", "5", "-palette=grey", "-v", "--size", "3"]),
+        )
+        .unwrap();
+        assert_eq!(parsed.get_silent("filter"), Some("5".to_string()));
+        assert_eq!(parsed.get_silent("-palette"), Some("grey".to_string()));
+        assert_eq!(parsed.get_silent("v"), Some(String::new()));
+        assert_eq!(parsed.get_silent("size"), None);
+        assert_eq!(parsed.get_usize("size").unwrap(), Some(3));
+        assert_eq!(parsed.get_silent_f64("filter").unwrap(), Some(5.0));
+        assert_eq!(parsed.get_silent_f64("radius").unwrap(), None);
+        // a silent flag that isn't a number is an error rather than a panic
+        let e = parsed.get_silent_f64("palette").unwrap_err();
+        assert!(e.to_string().contains("--palette"), "{}", e);
+    }
+
+    #[test]
+    fn test_parse_args_all_tools() {
+        // every tool's parameters can be parsed, with their defaults, when the required ones are given
+        let manager = ToolManager::new(".", &false).unwrap();
+        for name in &manager.tool_names {
+            let tool = manager.get_tool(&name.to_lowercase()).unwrap();
+            let json: Value = serde_json::from_str(&tool.get_tool_parameters()).unwrap();
+            let parameters: Vec<ToolParameter> = serde_json::from_value(json["parameters"].clone()).unwrap();
+            let mut flags = HashSet::new();
+            let mut tool_args = vec![];
+            for param in &parameters {
+                for flag in &param.flags {
+                    assert!(flags.insert(normalize_flag(flag)), "{}: duplicate flag {}", name, flag);
+                }
+                if !param.optional && param.default_value.is_none() {
+                    let value = match &param.parameter_type {
+                        ParameterType::Integer | ParameterType::Float | ParameterType::StringOrNumber => "1".to_string(),
+                        ParameterType::Boolean => "true".to_string(),
+                        ParameterType::OptionList(options) => options[0].clone(),
+                        _ => "file.tif".to_string(),
+                    };
+                    tool_args.push(format!("{}={}", param.flags[param.flags.len() - 1], value));
+                }
+            }
+            tool_args.push("-v".to_string());
+            if let Err(e) = parse_args(&parameters, &tool_args) {
+                panic!("{}: {}", name, e);
+            }
+        }
+    }
+
+    /// Splits a command line into its arguments, as a shell would, removing the quotes.
+    fn shell_words(line: &str) -> Vec<String> {
+        let mut words = vec![];
+        let mut word = String::new();
+        let mut in_word = false;
+        let mut quote = None;
+        for c in line.chars() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => word.push(c),
+                None if c == '"' || c == '\'' => {
+                    quote = Some(c);
+                    in_word = true;
+                }
+                None if c.is_whitespace() => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                }
+                None => {
+                    word.push(c);
+                    in_word = true;
+                }
+            }
+        }
+        if in_word {
+            words.push(word);
+        }
+        words
+    }
+
+    #[test]
+    fn test_parse_args_example_usage() {
+        // every tool's documented example parses
+        let manager = ToolManager::new(".", &false).unwrap();
+        let mut failures = vec![];
+        for name in &manager.tool_names {
+            let tool = manager.get_tool(&name.to_lowercase()).unwrap();
+            let json: Value = serde_json::from_str(&tool.get_tool_parameters()).unwrap();
+            let parameters: Vec<ToolParameter> = serde_json::from_value(json["parameters"].clone()).unwrap();
+            for line in tool.get_example_usage().lines() {
+                let line = match line.trim().strip_prefix(">>") {
+                    Some(l) => l,
+                    None => continue,
+                };
+                // the first word is the executable
+                let tool_args = shell_words(line).into_iter().skip(1).collect::<Vec<String>>();
+                if let Err(e) = parse_args(&parameters, &tool_args) {
+                    failures.push(format!("{}: {}", name, e));
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn test_parse_args_locale_and_quoting() {
+        let parsed = parse_args(
+            &parameters(),
+            &args(&["--inputs=\"dem, 2020.tif\";C:\\data\\O'Brien.tif", "'--output=my dem.tif'", "--sigma=1,5", "--size=1 000"]),
+        )
+        .unwrap();
+        assert_eq!(parsed.get_list("inputs"), vec!["dem, 2020.tif", "C:\\data\\O'Brien.tif"]);
+        assert_eq!(parsed.get_string("output"), Some("my dem.tif".to_string()));
+        assert_eq!(parsed.get_f64("sigma"), Some(1.5));
+        assert_eq!(parsed.get_usize("size").unwrap(), Some(1000));
+    }
+
+    #[test]
+    fn test_cancelled_tools_stop() {
+        // tools whose work is done by shared algorithms, rather than in their own loops, still
+        // stop before writing their outputs
+        let dir = std::env::temp_dir().join(format!("wbt_cancel_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut image = String::from("ncols 8\nnrows 8\nxllcorner 0\nyllcorner 0\ncellsize 1\nNODATA_value -9999\n");
+        for row in 0..8 {
+            let values: Vec<&str> = (0..8).map(|col| if row > 1 && row < 6 && col > 0 { "1" } else { "0" }).collect();
+            image.push_str(&values.join(" "));
+            image.push('\n');
+        }
+        fs::write(dir.join("image.asc"), image).unwrap();
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let reporter = CancellableProgress::new(&ConsoleProgress, token);
+        let manager = ToolManager::new(".", &false).unwrap();
+        let runs: Vec<(&str, Vec<&str>)> = vec![
+            ("skeletonize", vec!["-i=image.asc", "-o=out.asc"]),
+            ("morphologicalfilter", vec!["-i=image.asc", "-o=out.asc", "--operation=opening"]),
+            ("morphologicalreconstruction", vec!["--marker=image.asc", "--mask=image.asc", "-o=out.asc"]),
+            ("surfacewaterextraction", vec!["-i=image.asc", "-o=out.asc", "--threshold=0.5"]),
+        ];
+        for (name, args) in runs {
+            let tool = manager.get_tool(name).unwrap();
+            let args = args.iter().map(|a| a.to_string()).collect();
+            let e = tool.run(args, &dir.to_string_lossy(), false, &reporter).unwrap_err();
+            assert_eq!(e.kind(), ErrorKind::Interrupted, "{}: {}", name, e);
+            assert!(!dir.join("out.asc").exists(), "{} wrote its output", name);
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_tools_missing_last_value() {
+        // a flag given last without its value is an error, rather than a panic on args[i + 1]
+        let manager = ToolManager::new(".", &false).unwrap();
+        let runs: Vec<(&str, Vec<&str>)> = vec![
+            ("costdistance", vec!["--source=s.tif", "--cost=c.tif", "--out_accum=a.tif", "--out_backlink=b.tif", "--max_cost"]),
+            ("d8flowaccumulation", vec!["-i=dem.tif", "-o=out.tif", "--out_type"]),
+            ("lidaridwinterpolation", vec!["-i=in.las", "-o=out.tif", "--resolution"]),
+            ("slope", vec!["--dem=dem.tif", "-o=out.tif", "--zfactor"]),
+            ("extractstreams", vec!["--flow_accum=fa.tif", "-o=out.tif", "--threshold"]),
+        ];
+        for (name, args) in runs {
+            let tool = manager.get_tool(name).unwrap();
+            let args = args.iter().map(|a| a.to_string()).collect();
+            let e = tool.run(args, ".", false, &ConsoleProgress).unwrap_err();
+            assert_eq!(e.kind(), ErrorKind::InvalidInput, "{}: {}", name, e);
+            assert!(e.to_string().contains("requires a value"), "{}: {}", name, e);
+        }
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("1234.5"), Some(1234.5));
+        assert_eq!(parse_number("1234,5"), Some(1234.5));
+        assert_eq!(parse_number("1.234,5"), Some(1234.5));
+        assert_eq!(parse_number("1,234.5"), Some(1234.5));
+        assert_eq!(parse_number("1 234,5"), Some(1234.5));
+        assert_eq!(parse_number("1\u{a0}234\u{a0}567"), Some(1234567.0));
+        assert_eq!(parse_number("1'234.5"), Some(1234.5));
+        assert_eq!(parse_number("1.234.567"), Some(1234567.0));
+        assert_eq!(parse_number("-2,5e3"), Some(-2500.0));
+        assert_eq!(parse_number("\u{2212}0,25"), Some(-0.25));
+        assert_eq!(parse_number("1.2.3"), None);
+        assert_eq!(parse_number("12,34,567"), None);
+        assert_eq!(parse_number("1.234,5.6"), None);
+        assert_eq!(parse_number("abc"), None);
+        // a lone comma followed by three digits may group thousands or separate decimals
+        assert_eq!(parse_number("1,000"), None);
+        assert_eq!(parse_number("-12,500e3"), None);
+        assert_eq!(parse_number("0,125"), Some(0.125));
+        assert_eq!(parse_number("1,0005"), Some(1.0005));
+        assert_eq!(parse_number("1,000,000"), Some(1000000.0));
+        assert_eq!(parse_number("1,000.5"), Some(1000.5));
+    }
+
+    #[test]
+    fn test_split_list() {
+        assert_eq!(split_list("a.tif, b.tif"), vec!["a.tif", "b.tif"]);
+        assert_eq!(split_list("1,5; 2,5;"), vec!["1,5", "2,5"]);
+        assert_eq!(split_list("'my dem.tif',\"b, c.tif\""), vec!["my dem.tif", "b, c.tif"]);
+        assert_eq!(split_list("a\\,b.tif,c.tif"), vec!["a,b.tif", "c.tif"]);
+        assert_eq!(split_list("O'Brien.tif;D:\\x\\y.tif"), vec!["O'Brien.tif", "D:\\x\\y.tif"]);
+        assert!(split_list(" ; ").is_empty());
+    }
+}
@@ -0,0 +1,877 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::rendering::html::*;
+use whitebox_common::structures::{Array2D, Point2D};
+use crate::tools::*;
+use whitebox_vector::*;
+use kdtree::distance::squared_euclidean;
+use kdtree::KdTree;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::process::Command;
+
+/// This tool performs a complete cleaning of raw combine yield monitor point data (`--input`) in a single,
+/// configurable pass, writing the cleaned points (`--output`) and an HTML cleaning report (`--out_html`, which
+/// defaults to the output file name with an *.html* extension). The input points must be in the order in which
+/// they were logged and the crop yield values are read from a numeric attribute field (`--yield_field`). The
+/// following steps are applied, in order, and each point that is removed is flagged with the first step that
+/// removed it:
+///
+/// 1. **Flow delay correction (DELAY).** Grain takes several seconds to travel from the header to the yield
+///    sensor, so recorded yields lag behind the positions at which the grain was harvested. Each point is
+///    assigned the yield recorded a number of records later equal to the flow delay (`--flow_delay`, in seconds)
+///    divided by the logging interval (`--log_interval`, in seconds). Points near the end of a pass for which no
+///    later record exists within the same pass are removed.
+/// 2. **Speed filtering (SPEED).** Points with a ground speed, read from an optional field (`--speed_field`),
+///    outside of the range `--min_speed` to `--max_speed` are removed.
+/// 3. **Moisture filtering (MOISTURE).** Points with a grain moisture, read from an optional field
+///    (`--moisture_field`), outside of the range `--min_moisture` to `--max_moisture` are removed.
+/// 4. **Yield range filtering (YIELD).** Points with a delay-corrected yield outside of the range `--min_yield`
+///    to `--max_yield` are removed.
+/// 5. **Header-width overlap correction (OVERLAP).** The swath harvested between consecutive points, of the
+///    header width (`--width`, in map units), is compared with the area already harvested by earlier points.
+///    Because the yield monitor assumes a full header width, the yield of a point whose swath partly overlaps
+///    previously harvested ground is scaled up by the inverse of its effective width fraction. Points with an
+///    effective width fraction less than `--min_eff_width` are removed, since their yields are unreliable.
+/// 6. **Inlier detection (OUTLIER).** Each remaining point's corrected yield is compared with the median of the
+///    remaining points within a search radius (`--radius`, which defaults to four header widths). Points with a
+///    robust z-score, based on the median absolute deviation, greater than `--z_threshold` are removed as local
+///    outliers.
+///
+/// Pass boundaries, used by the flow delay and overlap steps, are read from an optional field (`--pass_field`).
+/// If no pass field is specified, a new pass is started wherever the change in heading between consecutive
+/// points exceeds `--max_change_in_heading` degrees or the distance between points is more than five times the
+/// median distance.
+///
+/// The output contains the retained points, with their original attributes and the added fields YIELD_CLN
+/// (the cleaned yield), EFF_WIDTH (the effective width fraction), and FLAG. If `--keep_all` is specified, the
+/// removed points are also output, with the FLAG field indicating the step that removed them. The report lists
+/// the number of points removed by each step and summary statistics of the yield data before and after cleaning.
+///
+/// # Reference
+/// Sudduth, K. A., and Drummond, S. T. (2007). Yield Editor: Software for removing errors from crop yield maps.
+/// *Agronomy Journal*, 99(6), 1471-1482.
+///
+/// # See Also
+/// `ManagementZones`
+pub struct CleanYieldData {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl CleanYieldData {
+    pub fn new() -> CleanYieldData {
+        // public constructor
+        let name = "CleanYieldData".to_string();
+        let toolbox = "Precision Agriculture".to_string();
+        let description = "Cleans yield monitor points with flow delay, filtering, overlap correction, and outlier removal.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Points File".to_owned(),
+            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            description: "Input yield monitor points vector file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Yield Field Name".to_owned(),
+            flags: vec!["--yield_field".to_owned()],
+            description: "Name of the attribute containing yield data.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Speed Field Name (optional)".to_owned(),
+            flags: vec!["--speed_field".to_owned()],
+            description: "Name of the attribute containing ground speed data.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Moisture Field Name (optional)".to_owned(),
+            flags: vec!["--moisture_field".to_owned()],
+            description: "Name of the attribute containing grain moisture data.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Number,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Pass Field Name (optional)".to_owned(),
+            flags: vec!["--pass_field".to_owned()],
+            description: "Name of the attribute containing pass line IDs.".to_owned(),
+            parameter_type: ParameterType::VectorAttributeField(
+                AttributeType::Any,
+                "--input".to_string(),
+            ),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output cleaned points vector file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output HTML Report File (optional)".to_owned(),
+            flags: vec!["--out_html".to_owned()],
+            description: "Output HTML cleaning report file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Html),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Header Width".to_owned(),
+            flags: vec!["--width".to_owned()],
+            description: "Combine header width, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("6.096".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Flow Delay (seconds)".to_owned(),
+            flags: vec!["--flow_delay".to_owned()],
+            description: "Grain flow delay, in seconds.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Logging Interval (seconds)".to_owned(),
+            flags: vec!["--log_interval".to_owned()],
+            description: "Time between logged points, in seconds.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Max Change In Heading".to_owned(),
+            flags: vec!["--max_change_in_heading".to_owned()],
+            description: "Max change in heading, in degrees, used to detect pass boundaries.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("25.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Speed (optional)".to_owned(),
+            flags: vec!["--min_speed".to_owned()],
+            description: "Minimum ground speed.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Speed (optional)".to_owned(),
+            flags: vec!["--max_speed".to_owned()],
+            description: "Maximum ground speed.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Moisture (optional)".to_owned(),
+            flags: vec!["--min_moisture".to_owned()],
+            description: "Minimum grain moisture.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Moisture (optional)".to_owned(),
+            flags: vec!["--max_moisture".to_owned()],
+            description: "Maximum grain moisture.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Yield".to_owned(),
+            flags: vec!["--min_yield".to_owned()],
+            description: "Minimum yield value.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Yield".to_owned(),
+            flags: vec!["--max_yield".to_owned()],
+            description: "Maximum yield value.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("99999.9".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Effective Width Fraction".to_owned(),
+            flags: vec!["--min_eff_width".to_owned()],
+            description: "Minimum fraction of the header width that must be unharvested ground.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Outlier Search Radius (optional)".to_owned(),
+            flags: vec!["--radius".to_owned()],
+            description: "Outlier detection search radius, in map units; defaults to four header widths.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Z-Score Threshold".to_owned(),
+            flags: vec!["--z_threshold".to_owned()],
+            description: "Robust z-score threshold used to identify local outliers.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("2.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Keep Removed Points?".to_owned(),
+            flags: vec!["--keep_all".to_owned()],
+            description: "Output removed points, flagged with the step that removed them?".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=yield_points.shp --yield_field=YIELD --speed_field=SPEED --moisture_field=MOISTURE -o=cleaned.shp --width=9.144 --flow_delay=12.0 --min_speed=2.0 --max_speed=10.0 --max_moisture=30.0 --z_threshold=3.0", short_exe, name).replace("*", &sep);
+
+        CleanYieldData {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for CleanYieldData {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let yield_field = parsed.get_string("yield_field").unwrap_or_default();
+        let speed_field = parsed.get_string("speed_field").unwrap_or_default();
+        let moisture_field = parsed.get_string("moisture_field").unwrap_or_default();
+        let pass_field = parsed.get_string("pass_field").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut output_html_file = parsed.get_string("out_html").unwrap_or_default();
+        let width = parsed.get_f64("width").unwrap_or(6.096f64);
+        let flow_delay = parsed.get_f64("flow_delay").unwrap_or(0f64);
+        let log_interval = parsed.get_f64("log_interval").unwrap_or(1f64);
+        let max_change_in_heading = parsed.get_f64("max_change_in_heading").unwrap_or(25f64);
+        let min_speed = parsed.get_f64("min_speed").unwrap_or(f64::NEG_INFINITY);
+        let max_speed = parsed.get_f64("max_speed").unwrap_or(f64::INFINITY);
+        let min_moisture = parsed.get_f64("min_moisture").unwrap_or(f64::NEG_INFINITY);
+        let max_moisture = parsed.get_f64("max_moisture").unwrap_or(f64::INFINITY);
+        let min_yield = parsed.get_f64("min_yield").unwrap_or(0f64);
+        let max_yield = parsed.get_f64("max_yield").unwrap_or(99999.9f64);
+        let min_eff_width = parsed.get_f64("min_eff_width").unwrap_or(0.5f64);
+        let mut radius = parsed.get_f64("radius").unwrap_or(f64::NAN);
+        let z_threshold = parsed.get_f64("z_threshold").unwrap_or(2.5f64);
+        let keep_all = parsed.get_bool("keep_all");
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        for file in [&mut input_file, &mut output_file, &mut output_html_file] {
+            *file = resolve_path(file, working_directory);
+        }
+        if output_html_file.is_empty() {
+            let p = path::Path::new(&output_file);
+            let mut extension = String::from(".");
+            let ext = p.extension().unwrap().to_str().unwrap();
+            extension.push_str(ext);
+            output_html_file = output_file.replace(&extension, ".html");
+        }
+        if width <= 0f64 || log_interval <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The header width (--width) and logging interval (--log_interval) must be greater than zero.",
+            ));
+        }
+        if radius.is_nan() {
+            radius = 4f64 * width;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Shapefile::read(&input_file)?;
+        if input.header.shape_type.base_shape_type() != ShapeType::Point {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input vector data must be of POINT base shape type.",
+            ));
+        }
+        for field in [&yield_field, &speed_field, &moisture_field, &pass_field] {
+            if !field.is_empty() && input.attributes.get_field_num(field).is_none() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The attribute field {} could not be found in the input file.", field),
+                ));
+            }
+        }
+        if yield_field.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The yield field (--yield_field) must be specified.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        let get_numeric = |record_num: usize, field: &str| -> f64 {
+            match input.attributes.get_value(record_num, field) {
+                FieldData::Int(val) => val as f64,
+                FieldData::Real(val) => val,
+                _ => f64::NAN,
+            }
+        };
+
+        let n = input.num_records;
+        let mut points = Vec::with_capacity(n);
+        let mut raw_yield = vec![f64::NAN; n];
+        for record_num in 0..n {
+            let record = input.get_record(record_num);
+            points.push(if record.num_points > 0 {
+                record.points[0]
+            } else {
+                Point2D::new(f64::NAN, f64::NAN)
+            });
+            raw_yield[record_num] = get_numeric(record_num, &yield_field);
+        }
+
+        // Identify passes.
+        let mut pass_ids = vec![0usize; n];
+        if !pass_field.is_empty() {
+            let mut pass_num = 0;
+            for record_num in 1..n {
+                if format!("{}", input.attributes.get_value(record_num, &pass_field))
+                    != format!("{}", input.attributes.get_value(record_num - 1, &pass_field))
+                {
+                    pass_num += 1;
+                }
+                pass_ids[record_num] = pass_num;
+            }
+        } else {
+            let mut steps: Vec<f64> = (1..n)
+                .map(|i| points[i].distance(&points[i - 1]))
+                .filter(|d| d.is_finite() && *d > 0f64)
+                .collect();
+            steps.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let max_step = if steps.len() > 0 {
+                5f64 * steps[steps.len() / 2]
+            } else {
+                f64::INFINITY
+            };
+            let mut pass_num = 0;
+            for i in 1..n {
+                let step = points[i].distance(&points[i - 1]);
+                let mut new_pass = !step.is_finite() || step > max_step;
+                if !new_pass && i > 1 && pass_ids[i - 1] == pass_ids[i - 2] {
+                    let heading_change = Point2D::change_in_heading(points[i - 2], points[i - 1], points[i]).to_degrees().abs();
+                    if heading_change > max_change_in_heading {
+                        new_pass = true;
+                    }
+                }
+                if new_pass {
+                    pass_num += 1;
+                }
+                pass_ids[i] = pass_num;
+            }
+        }
+        let num_passes = if n > 0 { pass_ids[n - 1] + 1 } else { 0 };
+
+        const FLAGS: [&str; 7] = ["OK", "DELAY", "SPEED", "MOISTURE", "YIELD", "OVERLAP", "OUTLIER"];
+        let mut flags = vec![0usize; n];
+
+        // 1. Flow delay correction
+        let lag = (flow_delay / log_interval).round() as isize;
+        let mut yields = vec![f64::NAN; n];
+        for i in 0..n {
+            let j = i as isize + lag;
+            if j < 0 || j >= n as isize || pass_ids[j as usize] != pass_ids[i] {
+                flags[i] = 1;
+            } else {
+                yields[i] = raw_yield[j as usize];
+                if !yields[i].is_finite() || !points[i].x.is_finite() {
+                    flags[i] = 4;
+                }
+            }
+        }
+
+        // 2. and 3. Speed and moisture filtering
+        for (field, min_val, max_val, flag) in [
+            (&speed_field, min_speed, max_speed, 2usize),
+            (&moisture_field, min_moisture, max_moisture, 3usize),
+        ] {
+            if field.is_empty() {
+                continue;
+            }
+            for i in 0..n {
+                if flags[i] == 0 {
+                    let v = get_numeric(i, field);
+                    if !v.is_finite() || v < min_val || v > max_val {
+                        flags[i] = flag;
+                    }
+                }
+            }
+        }
+
+        // 4. Yield range filtering
+        for i in 0..n {
+            if flags[i] == 0 && (yields[i] < min_yield || yields[i] > max_yield) {
+                flags[i] = 4;
+            }
+        }
+
+        // 5. Header-width overlap correction. All logged points are used to track the harvested area,
+        // since the ground was harvested regardless of whether the point is retained.
+        let mut eff_width = vec![1f64; n];
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for p in &points {
+            reporter.check_cancelled()?;
+            if p.x.is_finite() && p.y.is_finite() {
+                min_x = min_x.min(p.x);
+                min_y = min_y.min(p.y);
+                max_x = max_x.max(p.x);
+                max_y = max_y.max(p.y);
+            }
+        }
+        if min_x.is_finite() {
+            min_x -= width;
+            min_y -= width;
+            max_x += width;
+            max_y += width;
+            let mut cell_size = width / 10f64;
+            let max_cells = 25_000_000f64;
+            let grid_area = (max_x - min_x) * (max_y - min_y);
+            if grid_area / (cell_size * cell_size) > max_cells {
+                cell_size = (grid_area / max_cells).sqrt();
+            }
+            let rows = ((max_y - min_y) / cell_size).ceil() as isize;
+            let columns = ((max_x - min_x) / cell_size).ceil() as isize;
+            let mut harvested: Array2D<u8> = Array2D::new(rows, columns, 0u8, 1u8)?;
+            let half_width = width / 2f64;
+            for i in 0..n {
+                // The swath runs from the previous point in the pass to this point; the first point of
+                // a pass uses the length and direction of the following step.
+                let (a, b) = if i > 0 && pass_ids[i - 1] == pass_ids[i] {
+                    (points[i - 1], points[i])
+                } else if i + 1 < n && pass_ids[i + 1] == pass_ids[i] {
+                    (
+                        Point2D::new(2f64 * points[i].x - points[i + 1].x, 2f64 * points[i].y - points[i + 1].y),
+                        points[i],
+                    )
+                } else {
+                    continue;
+                };
+                let length = a.distance(&b);
+                if !length.is_finite() || length <= 0f64 {
+                    continue;
+                }
+                let (ux, uy) = ((b.x - a.x) / length, (b.y - a.y) / length);
+                let bb_min_x = a.x.min(b.x) - half_width;
+                let bb_max_x = a.x.max(b.x) + half_width;
+                let bb_min_y = a.y.min(b.y) - half_width;
+                let bb_max_y = a.y.max(b.y) + half_width;
+                let top = ((max_y - bb_max_y) / cell_size).floor().max(0f64) as isize;
+                let bottom = (((max_y - bb_min_y) / cell_size).ceil() as isize).min(rows - 1);
+                let left = ((bb_min_x - min_x) / cell_size).floor().max(0f64) as isize;
+                let right = (((bb_max_x - min_x) / cell_size).ceil() as isize).min(columns - 1);
+                let (mut total, mut unharvested) = (0usize, 0usize);
+                for row in top..=bottom {
+                    let y = max_y - (row as f64 + 0.5) * cell_size;
+                    for col in left..=right {
+                        let x = min_x + (col as f64 + 0.5) * cell_size;
+                        let along = (x - a.x) * ux + (y - a.y) * uy;
+                        let across = (x - a.x) * uy - (y - a.y) * ux;
+                        if along >= 0f64 && along < length && across.abs() <= half_width {
+                            total += 1;
+                            if harvested.get_value(row, col) == 0 {
+                                unharvested += 1;
+                                harvested.set_value(row, col, 1);
+                            }
+                        }
+                    }
+                }
+                if total > 0 {
+                    eff_width[i] = unharvested as f64 / total as f64;
+                }
+
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * i as f64 / (n - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Correcting overlap", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        }
+        for i in 0..n {
+            if flags[i] == 0 {
+                if eff_width[i] < min_eff_width || eff_width[i] <= 0f64 {
+                    flags[i] = 5;
+                } else {
+                    yields[i] /= eff_width[i];
+                }
+            }
+        }
+
+        // 6. Inlier detection
+        let mut tree = KdTree::with_capacity(2, 64);
+        for i in 0..n {
+            if flags[i] == 0 {
+                tree.add([points[i].x, points[i].y], i).unwrap();
+            }
+        }
+        let mut outliers = vec![];
+        let mut neighbours = vec![];
+        let mut deviations = vec![];
+        for i in 0..n {
+            if flags[i] != 0 {
+                continue;
+            }
+            let ret = tree
+                .within(&[points[i].x, points[i].y], radius * radius, &squared_euclidean)
+                .unwrap();
+            neighbours.clear();
+            for (_, &j) in &ret {
+                if j != i {
+                    neighbours.push(yields[j]);
+                }
+            }
+            if neighbours.len() < 5 {
+                continue;
+            }
+            let median = median_of(&mut neighbours);
+            deviations.clear();
+            for v in &neighbours {
+                deviations.push((v - median).abs());
+            }
+            let mad = 1.4826 * median_of(&mut deviations);
+            if mad > 0f64 && (yields[i] - median).abs() / mad > z_threshold {
+                outliers.push(i);
+            }
+
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * i as f64 / (n - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Detecting outliers", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+        for i in outliers {
+            flags[i] = 6;
+        }
+
+        // Create the output file
+        let mut output =
+            Shapefile::initialize_using_file(&output_file, &input, input.header.shape_type, true)?;
+        output
+            .attributes
+            .add_field(&AttributeField::new("YIELD_CLN", FieldDataType::Real, 12u8, 4u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("EFF_WIDTH", FieldDataType::Real, 6u8, 3u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("FLAG", FieldDataType::Text, 8u8, 0u8));
+        for record_num in 0..n {
+            if flags[record_num] != 0 && !keep_all {
+                continue;
+            }
+            let record = input.get_record(record_num);
+            output.add_record(record.clone());
+            let mut atts = input.attributes.get_record(record_num);
+            atts.push(if flags[record_num] == 0 {
+                FieldData::Real(yields[record_num])
+            } else {
+                FieldData::Null
+            });
+            atts.push(FieldData::Real(eff_width[record_num]));
+            atts.push(FieldData::Text(FLAGS[flags[record_num]].to_string()));
+            output.attributes.add_record(atts, false);
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        // create the output cleaning report
+        let mut counts = [0usize; 7];
+        for &flag in &flags {
+            counts[flag] += 1;
+        }
+        let raw_values: Vec<f64> = raw_yield.iter().cloned().filter(|v| v.is_finite()).collect();
+        let cleaned_values: Vec<f64> = (0..n).filter(|&i| flags[i] == 0).map(|i| yields[i]).collect();
+
+        let f = File::create(output_html_file.clone())?;
+        let mut writer = BufWriter::new(f);
+
+        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
+        <html>
+            <head>
+                <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
+                <title>Yield Data Cleaning Report</title>"#.as_bytes())?;
+
+        // get the style sheet
+        writer.write_all(&get_css().as_bytes())?;
+
+        writer.write_all(
+            &r#"
+            </head>
+            <body>
+                <h1>Yield Data Cleaning Report</h1>
+                "#
+            .as_bytes(),
+        )?;
+
+        writer.write_all((format!("<p><strong>Input</strong>: {}</p>", input_file)).as_bytes())?;
+        writer.write_all((format!("<p><strong>Num. passes</strong>: {}</p>", num_passes)).as_bytes())?;
+        writer.write_all(
+            (format!("<p><strong>Flow delay</strong>: {} s ({} records)</p>", flow_delay, lag)).as_bytes(),
+        )?;
+        writer.write_all((format!("<p><strong>Header width</strong>: {}</p>", width)).as_bytes())?;
+
+        //////////////////
+        // Points Table //
+        //////////////////
+        let descriptions = [
+            "Retained",
+            "Flow delay (no data within pass)",
+            "Speed filter",
+            "Moisture filter",
+            "Yield range filter",
+            "Header-width overlap",
+            "Local outlier",
+        ];
+        writer.write_all("<p><table>".as_bytes())?;
+        writer.write_all("<caption>Points Removed by Cleaning Step</caption>".as_bytes())?;
+        writer.write_all("<tr><th>Flag</th><th>Step</th><th>Num. Points</th><th>Percent</th></tr>".as_bytes())?;
+        for a in [1usize, 2, 3, 4, 5, 6, 0] {
+            writer.write_all(
+                &format!(
+                    "<tr><td>{}</td><td>{}</td><td class=\"numberCell\">{}</td><td class=\"numberCell\">{:.2}</td></tr>",
+                    FLAGS[a],
+                    descriptions[a],
+                    counts[a],
+                    100f64 * counts[a] as f64 / n.max(1) as f64
+                )
+                .as_bytes(),
+            )?;
+        }
+        writer.write_all("</table></p>".as_bytes())?;
+
+        //////////////////////
+        // Statistics Table //
+        //////////////////////
+        writer.write_all("<p><table>".as_bytes())?;
+        writer.write_all("<caption>Yield Summary Statistics</caption>".as_bytes())?;
+        writer.write_all("<tr><th>Statistic</th><th>Raw</th><th>Cleaned</th></tr>".as_bytes())?;
+        let raw_stats = summary_statistics(&raw_values);
+        let cleaned_stats = summary_statistics(&cleaned_values);
+        writer.write_all(
+            &format!(
+                "<tr><td>N</td><td class=\"numberCell\">{}</td><td class=\"numberCell\">{}</td></tr>",
+                raw_values.len(), cleaned_values.len()
+            )
+            .as_bytes(),
+        )?;
+        for (a, label) in ["Mean", "Std. Dev.", "CV (%)", "Min", "Max"].iter().enumerate() {
+            writer.write_all(
+                &format!(
+                    "<tr><td>{}</td><td class=\"numberCell\">{:.4}</td><td class=\"numberCell\">{:.4}</td></tr>",
+                    label, raw_stats[a], cleaned_stats[a]
+                )
+                .as_bytes(),
+            )?;
+        }
+        writer.write_all("</table></p>".as_bytes())?;
+
+        writer.write_all("</body>".as_bytes())?;
+
+        let _ = writer.flush();
+
+        if verbose {
+            if cfg!(target_os = "macos") || cfg!(target_os = "ios") {
+                let output = Command::new("open")
+                    .arg(output_html_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "windows") {
+                let output = Command::new("explorer.exe")
+                    .arg(output_html_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            } else if cfg!(target_os = "linux") {
+                let output = Command::new("xdg-open")
+                    .arg(output_html_file.clone())
+                    .output()
+                    .expect("failed to execute process");
+
+                let _ = output.stdout;
+            }
+
+            println!("Please see {} for output report.", output_html_file);
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the median of a non-empty set of values, reordering them in the process.
+fn median_of(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let m = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[m - 1] + values[m]) / 2f64
+    } else {
+        values[m]
+    }
+}
+
+/// Returns the mean, standard deviation, coefficient of variation, minimum, and maximum of a set of values.
+fn summary_statistics(values: &[f64]) -> [f64; 5] {
+    if values.is_empty() {
+        return [f64::NAN; 5];
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std_dev = (values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n).sqrt();
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    [mean, std_dev, 100f64 * std_dev / mean, min, max]
+}
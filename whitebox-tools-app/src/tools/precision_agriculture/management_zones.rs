@@ -0,0 +1,787 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use whitebox_common::structures::Array2D;
+use crate::tools::data_tools::trace_clump_polygons;
+use crate::tools::*;
+use whitebox_vector::*;
+use std::collections::VecDeque;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool delineates within-field management zones from multiple co-registered input rasters (`--inputs`),
+/// such as normalized yield maps, apparent soil electrical conductivity (EC), NDVI, and elevation derivatives
+/// (e.g. slope and wetness index). All inputs must share the same rows, columns, and extent. Each input is
+/// standardized to zero mean and unit variance, so that variables measured in different units contribute
+/// equally, and the grid cells are clustered into the specified number of zones (`--num_zones`) using fuzzy
+/// k-means (fuzzy c-means) clustering with a fuzziness exponent (`--fuzziness`), as in the Management Zone
+/// Analyst software (Fridgen et al., 2004). Cluster centres are initialized along the diagonal of the feature
+/// space and iteration continues until the largest change in cell membership is less than `--tolerance` or
+/// the maximum number of iterations (`--max_iterations`) is reached. Each cell is then assigned to the zone in
+/// which it has the greatest membership. Zones are numbered in order of increasing mean value of the first
+/// input raster, such that if yield is listed first, zone 1 is the lowest-yielding zone.
+///
+/// Because cell-by-cell classification produces speckled zones that are impractical to manage, the zones may be
+/// smoothed using a majority filter of a specified size (`--filter`; a value less than 3 disables smoothing).
+/// Contiguous zone patches that are smaller than a minimum area (`--min_area`, in map units squared) are then
+/// merged into the most common neighbouring zone, repeating until no patches below the minimum size remain.
+///
+/// The tool outputs a zone raster (`--output`) and, optionally, a raster of the confusion index (`--out_confusion`),
+/// i.e. one minus the difference between the two largest membership values of each cell, which highlights
+/// transitional areas where zone assignment is uncertain. An optional polygon vector (`--out_polys`) contains one
+/// feature for each contiguous zone patch, with attributes for the zone number (ZONE), patch area (AREA), and the
+/// mean (MEANn) and standard deviation (STDEVn) of each input raster within the patch, where *n* is the position of
+/// the raster in the input list. The fuzzy performance index (FPI) and normalized classification entropy (NCE) of
+/// the clustering are reported; running the tool with different numbers of zones and choosing the number at which
+/// both indices are minimized is a common way of selecting an appropriate number of zones.
+///
+/// # Reference
+/// Fridgen, J. J., Kitchen, N. R., Sudduth, K. A., Drummond, S. T., Wiebold, W. J., and Fraisse, C. W. (2004).
+/// Management Zone Analyst (MZA): Software for subfield management zone delineation. *Agronomy Journal*, 96(1),
+/// 100-108.
+///
+/// # See Also
+/// `KMeansClustering`, `ModifiedKMeansClustering`, `MajorityFilter`, `RasterToVectorPolygons`
+pub struct ManagementZones {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ManagementZones {
+    pub fn new() -> ManagementZones {
+        // public constructor
+        let name = "ManagementZones".to_string();
+        let toolbox = "Precision Agriculture".to_string();
+        let description = "Delineates management zones from multiple input rasters using fuzzy k-means clustering.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Files".to_owned(),
+            flags: vec!["-i".to_owned(), "--inputs".to_owned()],
+            description: "Input raster files (e.g. yield, EC, NDVI, slope).".to_owned(),
+            parameter_type: ParameterType::FileList(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Zones File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output management zones raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Zone Polygons File (optional)".to_owned(),
+            flags: vec!["--out_polys".to_owned()],
+            description: "Optional output zone polygons vector file, with per-zone statistics.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Confusion Index File (optional)".to_owned(),
+            flags: vec!["--out_confusion".to_owned()],
+            description: "Optional output confusion index raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Num. Zones".to_owned(),
+            flags: vec!["--num_zones".to_owned()],
+            description: "Number of management zones.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Fuzziness Exponent".to_owned(),
+            flags: vec!["--fuzziness".to_owned()],
+            description: "Fuzziness exponent (m), greater than 1.0.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Max. Iterations".to_owned(),
+            flags: vec!["--max_iterations".to_owned()],
+            description: "Maximum number of iterations.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("300".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Convergence Tolerance".to_owned(),
+            flags: vec!["--tolerance".to_owned()],
+            description: "Maximum change in membership between iterations before completion.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0001".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Smoothing Filter Size".to_owned(),
+            flags: vec!["--filter".to_owned()],
+            description: "Size of the majority filter used to smooth zones; less than 3 for no smoothing.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Zone Area".to_owned(),
+            flags: vec!["--min_area".to_owned()],
+            description: "Minimum area of contiguous zone patches, in map units squared.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" -i='yield.tif;ec.tif;ndvi.tif;slope.tif' -o=zones.tif --out_polys=zones.shp --out_confusion=ci.tif --num_zones=4 --fuzziness=1.3 --filter=5 --min_area=2500.0", short_exe, name).replace("*", &sep);
+
+        ManagementZones {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ManagementZones {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files_str = parsed.get_string("inputs").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut polys_file = parsed.get_string("out_polys").unwrap_or_default();
+        let mut confusion_file = parsed.get_string("out_confusion").unwrap_or_default();
+        let num_zones = parsed.get_usize("num_zones")?.unwrap_or(3usize);
+        let fuzziness = parsed.get_f64("fuzziness").unwrap_or(1.3f64);
+        let max_iterations = parsed.get_usize("max_iterations")?.unwrap_or(300usize);
+        let tolerance = parsed.get_f64("tolerance").unwrap_or(0.0001f64);
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(3usize);
+        let min_area = parsed.get_f64("min_area").unwrap_or(0f64);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        for file in [&mut output_file, &mut polys_file, &mut confusion_file] {
+            *file = resolve_path(file, working_directory);
+        }
+        if num_zones < 2 || num_zones > 255 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The number of zones (--num_zones) must be between 2 and 255.",
+            ));
+        }
+        if fuzziness <= 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The fuzziness exponent (--fuzziness) must be greater than 1.0.",
+            ));
+        }
+        if filter_size >= 3 && filter_size % 2 == 0 {
+            filter_size += 1;
+        }
+
+        let mut cmd = input_files_str.split(";");
+        let mut vec = cmd.collect::<Vec<&str>>();
+        if vec.len() == 1 {
+            cmd = input_files_str.split(",");
+            vec = cmd.collect::<Vec<&str>>();
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let mut inputs: Vec<Raster> = vec![];
+        for value in vec {
+            if !value.trim().is_empty() {
+                let mut input_file = value.trim().to_owned();
+                input_file = resolve_input_path(&input_file, working_directory)?;
+                inputs.push(Raster::new(&input_file, "r")?);
+            }
+        }
+        let num_inputs = inputs.len();
+        if num_inputs == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one input raster (--inputs) must be specified.",
+            ));
+        }
+        let rows = inputs[0].configs.rows as isize;
+        let columns = inputs[0].configs.columns as isize;
+        for input in &inputs {
+            if input.configs.rows as isize != rows || input.configs.columns as isize != columns {
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
+            }
+        }
+
+        let start = Instant::now();
+
+        // Gather the valid cells, i.e. those with data in every input.
+        let mut cells = vec![];
+        let mut data = vec![];
+        let mut z: f64;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            'col: for col in 0..columns {
+                for input in &inputs {
+                    z = input.get_value(row, col);
+                    if is_nodata(z, input.configs.nodata) {
+                        continue 'col;
+                    }
+                }
+                cells.push((row, col));
+                for input in &inputs {
+                    data.push(input.get_value(row, col));
+                }
+            }
+        }
+        let n = cells.len();
+        if n < num_zones {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "There are fewer valid grid cells than the number of zones.",
+            ));
+        }
+
+        // Standardize each input.
+        let p = num_inputs;
+        let mut means = vec![0f64; p];
+        let mut std_devs = vec![0f64; p];
+        for i in 0..n {
+            for d in 0..p {
+                means[d] += data[i * p + d];
+            }
+        }
+        for d in 0..p {
+            means[d] /= n as f64;
+        }
+        for i in 0..n {
+            for d in 0..p {
+                std_devs[d] += (data[i * p + d] - means[d]) * (data[i * p + d] - means[d]);
+            }
+        }
+        for d in 0..p {
+            std_devs[d] = (std_devs[d] / n as f64).sqrt();
+            if std_devs[d] == 0f64 {
+                std_devs[d] = 1f64;
+            }
+        }
+        let mut min_vals = vec![f64::INFINITY; p];
+        let mut max_vals = vec![f64::NEG_INFINITY; p];
+        for i in 0..n {
+            for d in 0..p {
+                data[i * p + d] = (data[i * p + d] - means[d]) / std_devs[d];
+                min_vals[d] = min_vals[d].min(data[i * p + d]);
+                max_vals[d] = max_vals[d].max(data[i * p + d]);
+            }
+        }
+
+        // Fuzzy k-means clustering, with centres initialized along the feature-space diagonal.
+        let k = num_zones;
+        let mut centres = vec![0f64; k * p];
+        for j in 0..k {
+            for d in 0..p {
+                centres[j * p + d] =
+                    min_vals[d] + (max_vals[d] - min_vals[d]) * (j as f64 + 0.5) / k as f64;
+            }
+        }
+        let exponent = 2f64 / (fuzziness - 1f64);
+        let mut membership = vec![0f64; n * k];
+        let mut dist = vec![0f64; k];
+        let mut num_iterations = 0;
+        for iteration in 0..max_iterations {
+            num_iterations = iteration + 1;
+            let mut max_change = 0f64;
+            for i in 0..n {
+                let mut zero_dist = k;
+                for j in 0..k {
+                    dist[j] = 0f64;
+                    for d in 0..p {
+                        z = data[i * p + d] - centres[j * p + d];
+                        dist[j] += z * z;
+                    }
+                    dist[j] = dist[j].sqrt();
+                    if dist[j] <= f64::EPSILON {
+                        zero_dist = j;
+                    }
+                }
+                for j in 0..k {
+                    let u = if zero_dist < k {
+                        if j == zero_dist { 1f64 } else { 0f64 }
+                    } else {
+                        1f64 / dist.iter().map(|dl| (dist[j] / dl).powf(exponent)).sum::<f64>()
+                    };
+                    max_change = max_change.max((u - membership[i * k + j]).abs());
+                    membership[i * k + j] = u;
+                }
+            }
+
+            let mut numerators = vec![0f64; k * p];
+            let mut denominators = vec![0f64; k];
+            for i in 0..n {
+                for j in 0..k {
+                    let w = membership[i * k + j].powf(fuzziness);
+                    denominators[j] += w;
+                    for d in 0..p {
+                        numerators[j * p + d] += w * data[i * p + d];
+                    }
+                }
+            }
+            for j in 0..k {
+                if denominators[j] > 0f64 {
+                    for d in 0..p {
+                        centres[j * p + d] = numerators[j * p + d] / denominators[j];
+                    }
+                }
+            }
+
+            if verbose {
+                println!(
+                    "Iteration {}: max. membership change = {:.6}",
+                    num_iterations, max_change
+                );
+            }
+            if max_change < tolerance {
+                break;
+            }
+        }
+
+        // Order the zones by the centre value of the first input.
+        let mut order: Vec<usize> = (0..k).collect();
+        order.sort_by(|a, b| centres[a * p].partial_cmp(&centres[b * p]).unwrap());
+        let mut zone_of_cluster = vec![0i16; k];
+        for (rank, &j) in order.iter().enumerate() {
+            zone_of_cluster[j] = rank as i16 + 1;
+        }
+
+        // Hard classification, confusion index, and partition validity indices.
+        let mut zones: Array2D<i16> = Array2D::new(rows, columns, 0i16, 0i16)?;
+        let mut confusion: Array2D<f64> = Array2D::new(rows, columns, -1f64, -1f64)?;
+        let mut partition_coef = 0f64;
+        let mut entropy = 0f64;
+        for i in 0..n {
+            let (mut first, mut second) = (0f64, 0f64);
+            let mut best = 0;
+            for j in 0..k {
+                let u = membership[i * k + j];
+                partition_coef += u * u;
+                if u > 0f64 {
+                    entropy -= u * u.ln();
+                }
+                if u > first {
+                    second = first;
+                    first = u;
+                    best = j;
+                } else if u > second {
+                    second = u;
+                }
+            }
+            zones.set_value(cells[i].0, cells[i].1, zone_of_cluster[best]);
+            confusion.set_value(cells[i].0, cells[i].1, 1f64 - (first - second));
+        }
+        partition_coef /= n as f64;
+        entropy /= n as f64;
+        let fpi = 1f64 - (k as f64 * partition_coef - 1f64) / (k as f64 - 1f64);
+        let nce = entropy / (k as f64).ln();
+        drop(membership);
+        drop(data);
+
+        // Spatial smoothing with a majority filter.
+        if filter_size >= 3 {
+            let half = (filter_size / 2) as isize;
+            let mut smoothed = zones.duplicate();
+            let mut counts = vec![0usize; k + 1];
+            let mut zn: i16;
+            for row in 0..rows {
+                for col in 0..columns {
+                    let zone = zones.get_value(row, col);
+                    if zone == 0 {
+                        continue;
+                    }
+                    for c in counts.iter_mut() {
+                        *c = 0;
+                    }
+                    for r in row - half..=row + half {
+                        for c in col - half..=col + half {
+                            zn = zones.get_value(r, c);
+                            if zn > 0 {
+                                counts[zn as usize] += 1;
+                            }
+                        }
+                    }
+                    let mut majority = zone;
+                    for zn in 1..=k {
+                        if counts[zn] > counts[majority as usize] {
+                            majority = zn as i16;
+                        }
+                    }
+                    smoothed.set_value(row, col, majority);
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Smoothing zones", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            zones = smoothed;
+        }
+
+        // Merge patches smaller than the minimum area into the most common neighbouring zone.
+        let cell_area = inputs[0].configs.resolution_x * inputs[0].configs.resolution_y;
+        let min_cells = (min_area / cell_area).ceil() as usize;
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        if min_cells > 1 {
+            loop {
+                reporter.check_cancelled()?;
+                let (_, patch_cells) = clump_zones(&zones)?;
+                let mut small_patches: Vec<usize> = (0..patch_cells.len())
+                    .filter(|&c| patch_cells[c].len() < min_cells)
+                    .collect();
+                small_patches.sort_by_key(|&c| patch_cells[c].len());
+                let mut changed = false;
+                for &c in &small_patches {
+                    let (r0, c0) = patch_cells[c][0];
+                    let zone = zones.get_value(r0, c0);
+                    let mut counts = vec![0usize; k + 1];
+                    for &(row, col) in &patch_cells[c] {
+                        for a in 0..8 {
+                            let zn = zones.get_value(row + dy[a], col + dx[a]);
+                            if zn > 0 && zn != zone {
+                                counts[zn as usize] += 1;
+                            }
+                        }
+                    }
+                    let mut new_zone = 0;
+                    for zn in 1..=k {
+                        if counts[zn] > counts[new_zone] {
+                            new_zone = zn;
+                        }
+                    }
+                    if new_zone > 0 {
+                        for &(row, col) in &patch_cells[c] {
+                            zones.set_value(row, col, new_zone as i16);
+                        }
+                        changed = true;
+                    }
+                }
+                if !changed {
+                    break;
+                }
+            }
+        }
+
+        // Per-zone summary statistics.
+        let mut zone_counts = vec![0usize; k + 1];
+        let mut zone_sums = vec![0f64; (k + 1) * p];
+        for &(row, col) in &cells {
+            reporter.check_cancelled()?;
+            let zone = zones.get_value(row, col) as usize;
+            zone_counts[zone] += 1;
+            for d in 0..p {
+                zone_sums[zone * p + d] += inputs[d].get_value(row, col);
+            }
+        }
+        if verbose {
+            println!("Fuzzy performance index (FPI): {:.4}", fpi);
+            println!("Normalized classification entropy (NCE): {:.4}", nce);
+            println!("Zone summary:");
+            let mut s = "Zone\tCells\tArea".to_string();
+            for d in 0..p {
+                s.push_str(&format!("\tMean ({})", inputs[d].get_short_filename()));
+            }
+            println!("{}", s);
+            for zone in 1..=k {
+                let mut s = format!(
+                    "{}\t{}\t{:.2}",
+                    zone,
+                    zone_counts[zone],
+                    zone_counts[zone] as f64 * cell_area
+                );
+                for d in 0..p {
+                    if zone_counts[zone] > 0 {
+                        s.push_str(&format!("\t{:.4}", zone_sums[zone * p + d] / zone_counts[zone] as f64));
+                    } else {
+                        s.push_str("\t-");
+                    }
+                }
+                println!("{}", s);
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if !polys_file.is_empty() {
+            if verbose {
+                println!("Creating zone polygons...")
+            };
+            let (patches, patch_cells) = clump_zones(&zones)?;
+            let geometries =
+                trace_clump_polygons(&patches, patch_cells.len(), &inputs[0].configs, verbose, reporter)?;
+
+            let mut output = Shapefile::new(&polys_file, ShapeType::Polygon)?;
+            output.projection = inputs[0].configs.coordinate_ref_system_wkt.clone();
+            output
+                .attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 10u8, 0u8));
+            output
+                .attributes
+                .add_field(&AttributeField::new("ZONE", FieldDataType::Int, 4u8, 0u8));
+            output
+                .attributes
+                .add_field(&AttributeField::new("AREA", FieldDataType::Real, 14u8, 2u8));
+            for d in 0..p {
+                output.attributes.add_field(&AttributeField::new(
+                    &format!("MEAN{}", d + 1),
+                    FieldDataType::Real,
+                    14u8,
+                    5u8,
+                ));
+                output.attributes.add_field(&AttributeField::new(
+                    &format!("STDEV{}", d + 1),
+                    FieldDataType::Real,
+                    14u8,
+                    5u8,
+                ));
+            }
+
+            let mut fid = 1;
+            for c in 0..patch_cells.len() {
+                reporter.check_cancelled()?;
+                if geometries[c].num_parts == 0 {
+                    continue;
+                }
+                let (r0, c0) = patch_cells[c][0];
+                let num_cells = patch_cells[c].len() as f64;
+                let mut atts = vec![
+                    FieldData::Int(fid),
+                    FieldData::Int(zones.get_value(r0, c0) as i32),
+                    FieldData::Real(num_cells * cell_area),
+                ];
+                for d in 0..p {
+                    let (mut sum, mut sq_sum) = (0f64, 0f64);
+                    for &(row, col) in &patch_cells[c] {
+                        z = inputs[d].get_value(row, col);
+                        sum += z;
+                        sq_sum += z * z;
+                    }
+                    let mean = sum / num_cells;
+                    atts.push(FieldData::Real(mean));
+                    atts.push(FieldData::Real((sq_sum / num_cells - mean * mean).max(0f64).sqrt()));
+                }
+                output.add_record(geometries[c].clone());
+                output.attributes.add_record(atts, false);
+                fid += 1;
+            }
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &inputs[0]);
+        let out_nodata = -32768f64;
+        output.configs.nodata = out_nodata;
+        output.configs.data_type = DataType::I16;
+        output.configs.palette = "qual.plt".to_string();
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.reinitialize_values(out_nodata);
+        for &(row, col) in &cells {
+            output.set_value(row, col, zones.get_value(row, col) as f64);
+        }
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input files: {}", input_files_str));
+        output.add_metadata_entry(format!("Num. zones: {}", num_zones));
+        output.add_metadata_entry(format!("Fuzziness exponent: {}", fuzziness));
+        output.add_metadata_entry(format!("Num. iterations: {}", num_iterations));
+        output.add_metadata_entry(format!("Filter size: {}", filter_size));
+        output.add_metadata_entry(format!("Min. zone area: {}", min_area));
+        output.add_metadata_entry(format!("FPI: {:.4}", fpi));
+        output.add_metadata_entry(format!("NCE: {:.4}", nce));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !confusion_file.is_empty() {
+            let mut output = Raster::initialize_using_file(&confusion_file, &inputs[0]);
+            output.configs.nodata = out_nodata;
+            output.configs.data_type = DataType::F32;
+            output.configs.palette = "spectrum.plt".to_string();
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.reinitialize_values(out_nodata);
+            for &(row, col) in &cells {
+                output.set_value(row, col, confusion.get_value(row, col));
+            }
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry("Confusion index".to_string());
+            output.add_metadata_entry(format!("Num. zones: {}", num_zones));
+            output.add_metadata_entry(format!("Fuzziness exponent: {}", fuzziness));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Groups the cells of a zone grid into 8-connected patches of equal zone, ignoring cells with a zone of 0.
+/// Returns a grid of patch identifiers, starting at 1, and the cells belonging to each patch.
+fn clump_zones(zones: &Array2D<i16>) -> Result<(Array2D<u32>, Vec<Vec<(isize, isize)>>), Error> {
+    let rows = zones.rows;
+    let columns = zones.columns;
+    let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+    let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+    let mut patches: Array2D<u32> = Array2D::new(rows, columns, 0u32, 0u32)?;
+    let mut patch_cells = vec![];
+    let mut queue = VecDeque::new();
+    let (mut rn, mut cn): (isize, isize);
+    for row in 0..rows {
+        for col in 0..columns {
+            let zone = zones.get_value(row, col);
+            if zone == 0 || patches.get_value(row, col) != 0 {
+                continue;
+            }
+            let patch_id = patch_cells.len() as u32 + 1;
+            let mut cells = vec![(row, col)];
+            patches.set_value(row, col, patch_id);
+            queue.push_back((row, col));
+            while let Some((r, c)) = queue.pop_front() {
+                for a in 0..8 {
+                    rn = r + dy[a];
+                    cn = c + dx[a];
+                    if zones.get_value(rn, cn) == zone && patches.get_value(rn, cn) == 0 {
+                        patches.set_value(rn, cn, patch_id);
+                        cells.push((rn, cn));
+                        queue.push_back((rn, cn));
+                    }
+                }
+            }
+            patch_cells.push(cells);
+        }
+    }
+    Ok((patches, patch_cells))
+}
@@ -0,0 +1,7 @@
+// private sub-module defined in other files
+mod clean_yield_data;
+mod management_zones;
+
+// exports identifiers from private sub-modules in the current module namespace
+pub use self::clean_yield_data::CleanYieldData;
+pub use self::management_zones::ManagementZones;
@@ -0,0 +1,208 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Presets are named sets of tool parameter values, e.g. 'lidar_dem_1m', stored in JSON files
+keyed by tool name and then by preset name:
+
+    {
+      "LidarTINGridding": {
+        "lidar_dem_1m": { "resolution": 1.0, "returns": "last", "exclude_cls": "7,18" }
+      },
+      "FillDepressions": {
+        "default": { "fix_flats": true }
+      }
+    }
+
+Parameters are named by any of their flags, with or without the leading dashes. A preset is
+applied to a run with the --preset=name flag, and a tool's preset named 'default', if it has
+one, is applied to every run of the tool that doesn't name a preset (--preset=none disables
+it). Values given explicitly in a run always take precedence over those of its preset.
+
+Presets are read from the user's presets file (presets.json in the WhiteboxTools directory of the
+user's configuration directory) and from a shared presets file named by the presets_file setting,
+which allows a team to standardize tool configurations across analysts; the user's presets
+replace shared presets of the same name.
+*/
+
+use serde_json::{Map, Value};
+use whitebox_common::configs::user_config_dir;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+
+/// The presets of a tool, keyed by preset name, each mapping parameter flags to values.
+pub type ToolPresets = BTreeMap<String, Map<String, Value>>;
+
+/// The presets of all tools, keyed by tool name.
+pub type Presets = BTreeMap<String, ToolPresets>;
+
+/// Returns the user's presets file, i.e. presets.json within the WhiteboxTools directory of the
+/// user's configuration directory (%APPDATA% on Windows, and otherwise $XDG_CONFIG_HOME or
+/// ~/.config), whether or not it exists.
+pub fn user_presets_file() -> Option<PathBuf> {
+    user_config_dir().map(|d| d.join("presets.json"))
+}
+
+/// Reads the presets of the shared presets file, if one is named, and of the user's presets file.
+/// Missing files contain no presets.
+pub fn read_presets(shared_file: &str) -> Result<Presets, Error> {
+    let mut presets = Presets::new();
+    let mut files = vec![];
+    if !shared_file.trim().is_empty() {
+        files.push(PathBuf::from(shared_file.trim()));
+    }
+    if let Some(f) = user_presets_file() {
+        files.push(f);
+    }
+    for file in files {
+        let contents = match fs::read_to_string(&file) {
+            Ok(c) => c,
+            Err(ref e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        let file_presets: Presets = serde_json::from_str(&contents).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("The presets file {} could not be read: {}", file.display(), e),
+            )
+        })?;
+        for (tool, tool_presets) in file_presets {
+            let key = presets
+                .keys()
+                .find(|k| same_tool(k, &tool))
+                .cloned()
+                .unwrap_or(tool);
+            presets.entry(key).or_default().extend(tool_presets);
+        }
+    }
+    Ok(presets)
+}
+
+/// Returns the presets of a tool. Tool names are matched ignoring case and underscores, such that
+/// 'lidar_tin_gridding' names the presets of LidarTINGridding.
+pub fn tool_presets<'a>(presets: &'a Presets, tool_name: &str) -> Option<&'a ToolPresets> {
+    presets.iter().find(|(k, _)| same_tool(k, tool_name)).map(|(_, v)| v)
+}
+
+fn same_tool(a: &str, b: &str) -> bool {
+    a.replace("_", "").to_lowercase() == b.replace("_", "").to_lowercase()
+}
+
+fn normalize_flag(flag: &str) -> String {
+    flag.trim().trim_start_matches('-').to_lowercase()
+}
+
+/// Removes a --preset flag from the arguments of a run, returning its value, which is empty if
+/// the flag was not used.
+pub fn take_preset_arg(args: &mut Vec<String>) -> String {
+    let mut preset = String::new();
+    args.retain(|arg| {
+        let a = arg.trim().replace("\"", "").replace("\'", "");
+        let (flag, value) = a.split_once('=').unwrap_or((&a, ""));
+        if normalize_flag(flag) == "preset" && flag.starts_with('-') {
+            preset = value.trim().to_string();
+            false
+        } else {
+            true
+        }
+    });
+    preset
+}
+
+/// Adds the values of a tool's preset to the arguments of a run, for the parameters that the
+/// arguments don't already supply. `preset` is the preset name; if it is empty, the tool's
+/// 'default' preset is applied, if it has one, and if it is 'none', no preset is applied. Returns
+/// the name of the preset that was applied, if any, along with the added arguments.
+///
+/// `parameters` are the tool's parameters, as returned by `get_tool_parameters`, and `supplied`
+/// lists the parameters that the run's arguments supply.
+pub fn preset_args(
+    presets: &Presets,
+    tool_name: &str,
+    parameters: &str,
+    supplied: &[Value],
+    preset: &str,
+) -> Result<Option<(String, Vec<String>)>, Error> {
+    let preset = preset.trim();
+    if preset.to_lowercase() == "none" {
+        return Ok(None);
+    }
+    let tool_presets = tool_presets(presets, tool_name);
+    let (name, values) = match tool_presets.and_then(|p| {
+        let name = if preset.is_empty() { "default" } else { preset };
+        p.iter().find(|(k, _)| k.to_lowercase() == name.to_lowercase())
+    }) {
+        Some(p) => p,
+        None if preset.is_empty() => return Ok(None),
+        None => {
+            let available = tool_presets
+                .map(|p| p.keys().cloned().collect::<Vec<String>>().join(", "))
+                .unwrap_or_default();
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                if available.is_empty() {
+                    format!("The tool {} has no presets, and so the preset '{}' cannot be applied.", tool_name, preset)
+                } else {
+                    format!("The tool {} has no preset named '{}'; its presets are: {}.", tool_name, preset, available)
+                },
+            ));
+        }
+    };
+
+    let parameters: Value = serde_json::from_str(parameters)?;
+    let params = parameters["parameters"].as_array().cloned().unwrap_or_default();
+    let mut args = vec![];
+    for (key, value) in values {
+        let flag = normalize_flag(key);
+        let param = params.iter().find(|p| {
+            p["flags"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|f| f.as_str())
+                .any(|f| normalize_flag(f) == flag)
+        });
+        let param = match param {
+            Some(p) => p,
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("The preset '{}' sets '{}', which is not a parameter of the tool {}.", name, key, tool_name),
+                ))
+            }
+        };
+        if supplied.iter().any(|s| s["flags"] == param["flags"]) {
+            continue;
+        }
+        let long_flag = param["flags"]
+            .as_array()
+            .and_then(|f| f.last())
+            .and_then(|f| f.as_str())
+            .unwrap_or(key)
+            .to_string();
+        let value = match value {
+            Value::Null => continue,
+            Value::Bool(true) => {
+                args.push(long_flag);
+                continue;
+            }
+            Value::String(s) => s.clone(),
+            Value::Array(items) => items
+                .iter()
+                .map(|v| match v {
+                    Value::String(s) => s.clone(),
+                    _ => v.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(";"),
+            _ => value.to_string(),
+        };
+        args.push(format!("{}={}", long_flag, value));
+    }
+    Ok(Some((name.clone(), args)))
+}
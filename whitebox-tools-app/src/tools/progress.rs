@@ -0,0 +1,225 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Tools report their progress through a `ProgressReporter`, which is passed to
+`WhiteboxTool::run`, rather than printing it directly, so that programs that use the library,
+e.g. GUI frontends, can receive progress as structured events. Tools only report progress when
+they are run in verbose mode. The command-line program uses the `ConsoleProgress` reporter, which
+prints progress as it always has been, e.g. 'Progress: 45%', unless it is run with
+--progress=json, in which case the `JsonProgress` reporter prints one JSON object per event, for
+the benefit of frontends, such as the Python wrapper, that read the program's output.
+
+The reporter is also how a caller cancels a running tool. Tools call `check_cancelled` within
+their processing loops, whether or not they are in verbose mode, and return its `Interrupted`
+error, which stops the tool before it writes any further outputs; outputs are never cancelled
+part-way through being written. A caller cancels a tool by returning true from `is_cancelled`,
+e.g. by wrapping its reporter in a `CancellableProgress` and cancelling the `CancellationToken`
+from another thread.
+
+Worker threads can't borrow the reporter, and so a tool gives them its `cancellation_token`
+instead. Workers that report their own progress send it as a `WorkerMessage` over the channel
+that carries their results, and the tool's thread passes it on to the reporter with `recv_data`. Workers that send their results row by row stop when the tool, having returned, no
+longer receives them; those that send their results only once they're done also check the token,
+so that the tool isn't kept waiting for them.
+*/
+
+use serde_json::json;
+use std::io::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvError};
+use std::sync::Arc;
+use whitebox_common::errors::ErrorCause;
+
+/// Receives the progress of a running tool.
+pub trait ProgressReporter: Sync {
+    /// Reports that a tool has completed `percent` percent of a stage of its processing, which
+    /// is described by `label`, e.g. 'Progress' or 'Flow accumulation'.
+    fn progress(&self, label: &str, percent: usize);
+
+    /// Returns true if the caller has asked the tool to stop. The default never cancels.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+
+    /// Returns an `Interrupted` error if the caller has asked the tool to stop.
+    fn check_cancelled(&self) -> Result<(), Error> {
+        if self.is_cancelled() {
+            return Err(ErrorCause::Cancelled.error("The tool was cancelled."));
+        }
+        Ok(())
+    }
+
+    /// Returns a token that is cancelled when the tool should stop, which tools pass to worker
+    /// threads that cannot borrow the reporter. The default token is never cancelled, and so a
+    /// reporter that overrides `is_cancelled` should also override this method.
+    fn cancellation_token(&self) -> CancellationToken {
+        CancellationToken::new()
+    }
+}
+
+/// Prints progress to the standard output, e.g. 'Progress: 45%'. This is the default reporter.
+#[derive(Default, Clone, Copy)]
+pub struct ConsoleProgress;
+
+impl ProgressReporter for ConsoleProgress {
+    fn progress(&self, label: &str, percent: usize) {
+        println!("{}: {}%", label, percent);
+    }
+}
+
+/// Prints progress to the standard output as JSON objects, one per line, e.g.
+/// `{"label":"Progress","progress":45}`.
+#[derive(Default, Clone, Copy)]
+pub struct JsonProgress;
+
+impl ProgressReporter for JsonProgress {
+    fn progress(&self, label: &str, percent: usize) {
+        println!("{}", json!({ "label": label, "progress": percent }));
+    }
+}
+
+/// A flag with which one thread can cancel a tool running in another. Clones share the flag.
+#[derive(Default, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    /// Other tokens whose cancellation also cancels this one (see `combine`).
+    linked: Vec<CancellationToken>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Asks the tools using this token to stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.linked.iter().any(|t| t.is_cancelled())
+    }
+
+    /// Clears the flag, so that the token can be used for another run. Tokens combined with this one
+    /// are not reset.
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Relaxed);
+    }
+
+    /// Returns a token that shares this token's flag and is also cancelled when `other` is.
+    pub fn combine(&self, other: &CancellationToken) -> CancellationToken {
+        let mut token = self.clone();
+        token.linked.push(other.clone());
+        token
+    }
+}
+
+/// Passes progress on to another reporter and cancels the tool when its token is cancelled.
+pub struct CancellableProgress<'a> {
+    reporter: &'a dyn ProgressReporter,
+    token: CancellationToken,
+}
+
+impl<'a> CancellableProgress<'a> {
+    pub fn new(reporter: &'a dyn ProgressReporter, token: CancellationToken) -> CancellableProgress<'a> {
+        CancellableProgress { reporter, token }
+    }
+}
+
+impl<'a> ProgressReporter for CancellableProgress<'a> {
+    fn progress(&self, label: &str, percent: usize) {
+        self.reporter.progress(label, percent);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled() || self.reporter.is_cancelled()
+    }
+
+    fn cancellation_token(&self) -> CancellationToken {
+        // the wrapped reporter may itself be cancellable, e.g. when reporters are nested
+        self.token.combine(&self.reporter.cancellation_token())
+    }
+}
+
+/// A message sent by a worker thread to the thread running a tool.
+pub enum WorkerMessage<T> {
+    /// The worker has completed `percent` percent of the stage described by the label.
+    Progress(&'static str, usize),
+    /// The worker's results, or a part of them.
+    Data(T),
+}
+
+/// Waits for the next data sent by a tool's worker threads, passing on any progress that they
+/// report in the meantime. Returns an error once all of the workers have stopped.
+pub fn recv_data<T>(
+    rx: &Receiver<WorkerMessage<T>>,
+    reporter: &dyn ProgressReporter,
+) -> Result<T, RecvError> {
+    loop {
+        match rx.recv()? {
+            WorkerMessage::Progress(label, percent) => reporter.progress(label, percent),
+            WorkerMessage::Data(data) => return Ok(data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{recv_data, CancellableProgress, CancellationToken, ConsoleProgress, ProgressReporter, WorkerMessage};
+    use std::sync::mpsc;
+    use std::sync::Mutex;
+    use whitebox_common::utils::spawn;
+
+    #[derive(Default)]
+    struct RecordingProgress {
+        events: Mutex<Vec<(String, usize)>>,
+    }
+
+    impl ProgressReporter for RecordingProgress {
+        fn progress(&self, label: &str, percent: usize) {
+            self.events.lock().unwrap().push((label.to_string(), percent));
+        }
+    }
+
+    #[test]
+    fn test_recv_data() {
+        let (tx, rx) = mpsc::channel();
+        spawn(move || {
+            tx.send(WorkerMessage::Progress("Reading points", 50)).unwrap();
+            tx.send(WorkerMessage::Data(1)).unwrap();
+            tx.send(WorkerMessage::Progress("Progress", 100)).unwrap();
+            tx.send(WorkerMessage::Data(2)).unwrap();
+        });
+        let reporter = RecordingProgress::default();
+        assert_eq!(recv_data(&rx, &reporter), Ok(1));
+        assert_eq!(recv_data(&rx, &reporter), Ok(2));
+        assert!(recv_data(&rx, &reporter).is_err());
+        assert_eq!(
+            *reporter.events.lock().unwrap(),
+            vec![("Reading points".to_string(), 50), ("Progress".to_string(), 100)]
+        );
+    }
+
+    #[test]
+    fn test_nested_cancellation() {
+        let inner_token = CancellationToken::new();
+        let outer_token = CancellationToken::new();
+        let inner = CancellableProgress::new(&ConsoleProgress, inner_token.clone());
+        let outer = CancellableProgress::new(&inner, outer_token.clone());
+        // workers may be given the token before the tool is cancelled
+        let worker_token = outer.cancellation_token();
+        assert!(!worker_token.is_cancelled());
+        inner_token.cancel();
+        assert!(outer.is_cancelled());
+        assert!(worker_token.is_cancelled());
+        inner_token.reset();
+        assert!(!worker_token.is_cancelled());
+        outer_token.cancel();
+        assert!(worker_token.is_cancelled());
+        assert!(!inner.cancellation_token().is_cancelled());
+    }
+}
@@ -0,0 +1,148 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Provenance records describe the tool run that created an output file: the tool and
+WhiteboxTools version, every parameter supplied, and the SHA-256 hashes of the input and
+output files. They are written as JSON sidecars (e.g. dem_filled.tif.provenance.json) next
+to the outputs. When an input file has a sidecar of its own, and the input's hash matches
+the one recorded there, that record is nested within the input's entry, so that the record
+of a final output holds the full processing history leading up to it.
+*/
+
+use super::parse_tool_args;
+use chrono::prelude::*;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, Error, Read};
+use std::path::Path;
+use whitebox_common::utils::{is_url, resolve_path};
+
+const VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSION");
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct Provenance {
+    pub tool: String,
+    pub version: String,
+    pub timestamp: String,
+    pub working_directory: String,
+    /// The parameters supplied to the tool, as (flag, value) pairs in the order given.
+    pub parameters: Vec<(String, String)>,
+    pub inputs: Vec<ProvenanceInput>,
+    pub outputs: Vec<ProvenanceOutput>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct ProvenanceInput {
+    pub file: String,
+    pub sha256: Option<String>,
+    /// The provenance record of the input file, if it was created by a tool with provenance
+    /// recording enabled and has not been modified since.
+    pub provenance: Option<Box<Provenance>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct ProvenanceOutput {
+    pub file: String,
+    pub sha256: Option<String>,
+}
+
+/// Returns the name of the provenance sidecar file of a data file.
+pub(crate) fn sidecar_file_name(file_name: &str) -> String {
+    format!("{}.provenance.json", file_name)
+}
+
+/// Reads the provenance record of a data file, if it has one.
+pub(crate) fn read_provenance(file_name: &str) -> Option<Provenance> {
+    let contents = fs::read_to_string(sidecar_file_name(file_name)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// Writes a provenance sidecar for each output file of a completed tool run. `parameters` is the
+/// tool's parameter description, as returned by `get_tool_parameters`.
+pub(crate) fn write_provenance(
+    tool_name: &str,
+    parameters: &str,
+    args: &[String],
+    working_directory: &str,
+) -> Result<(), Error> {
+    let mut record = Provenance {
+        tool: tool_name.to_string(),
+        version: VERSION.unwrap_or("unknown").to_string(),
+        timestamp: Local::now().to_rfc3339(),
+        working_directory: working_directory.to_string(),
+        parameters: vec![],
+        inputs: vec![],
+        outputs: vec![],
+    };
+    for (param, value) in parse_tool_args(parameters, args)? {
+        if value.trim().is_empty() {
+            continue;
+        }
+        let flag = param["flags"]
+            .as_array()
+            .and_then(|flags| flags.last())
+            .and_then(|f| f.as_str())
+            .unwrap_or("")
+            .to_string();
+        let parameter_type = &param["parameter_type"];
+        if parameter_type.get("NewFile").is_some() {
+            let file = resolve_path(&value, working_directory);
+            let sha256 = hash_file(&file).ok();
+            record.outputs.push(ProvenanceOutput { file, sha256 });
+        } else if parameter_type.get("FileList").is_some() {
+            let delimiter = if value.contains(';') { ';' } else { ',' };
+            for file in value.split(delimiter).filter(|f| !f.trim().is_empty()) {
+                record.inputs.push(describe_input(&resolve_path(file, working_directory)));
+            }
+        } else if parameter_type.get("ExistingFile").is_some()
+            || (parameter_type.get("ExistingFileOrFloat").is_some() && value.trim().parse::<f64>().is_err())
+        {
+            record.inputs.push(describe_input(&resolve_path(&value, working_directory)));
+        }
+        record.parameters.push((flag, value));
+    }
+
+    let json = serde_json::to_string_pretty(&record)?;
+    for output in &record.outputs {
+        if Path::new(&output.file).is_file() {
+            fs::write(sidecar_file_name(&output.file), &json)?;
+        }
+    }
+    Ok(())
+}
+
+fn describe_input(file_name: &str) -> ProvenanceInput {
+    let sha256 = if is_url(file_name) { None } else { hash_file(file_name).ok() };
+    // A sidecar is only trusted if the file still matches the output that it describes.
+    let provenance = read_provenance(file_name).filter(|p| {
+        sha256.is_some() && p.outputs.iter().any(|o| o.sha256 == sha256)
+    });
+    ProvenanceInput {
+        file: file_name.to_string(),
+        sha256,
+        provenance: provenance.map(Box::new),
+    }
+}
+
+pub(crate) fn hash_file(file_name: &str) -> Result<String, Error> {
+    let mut reader = BufReader::new(File::open(file_name)?);
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1 << 20];
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
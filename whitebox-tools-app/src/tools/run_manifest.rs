@@ -0,0 +1,104 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: A run manifest is a local JSON Lines file, named by the run_manifest setting, to which
+one record is appended for every tool run: the tool, its arguments, the working directory,
+the start time, the duration, whether the run succeeded, and the output files that it
+created. Nothing is sent anywhere. The manifest allows large batch jobs to be audited and the
+failed runs to be repeated with the --rerun_failed flag.
+*/
+
+use chrono::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{Error, ErrorKind, Write};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ManifestEntry {
+    pub timestamp: String,
+    pub tool: String,
+    pub working_directory: String,
+    pub args: Vec<String>,
+    pub duration_seconds: f64,
+    /// Either 'succeeded', 'failed', or 'cancelled'.
+    pub status: String,
+    pub error: Option<String>,
+    pub outputs: Vec<String>,
+}
+
+impl ManifestEntry {
+    pub fn new(tool: &str, working_directory: &str, args: &[String], start: DateTime<Local>) -> ManifestEntry {
+        ManifestEntry {
+            timestamp: start.to_rfc3339(),
+            tool: tool.to_string(),
+            working_directory: working_directory.to_string(),
+            args: args.to_vec(),
+            duration_seconds: 0f64,
+            status: String::from("succeeded"),
+            error: None,
+            outputs: vec![],
+        }
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.status == "succeeded"
+    }
+}
+
+/// Appends an entry to the manifest file, creating the file if necessary.
+pub fn append_entry(manifest_file: &str, entry: &ManifestEntry) -> Result<(), Error> {
+    let line = serde_json::to_string(entry)?;
+    let mut f = OpenOptions::new().create(true).append(true).open(manifest_file)?;
+    // a single write per line keeps entries intact when several processes share a manifest
+    f.write_all(format!("{}\n", line).as_bytes())?;
+    Ok(())
+}
+
+/// Reads the entries of a manifest file, skipping any lines that cannot be parsed.
+pub fn read_entries(manifest_file: &str) -> Result<Vec<ManifestEntry>, Error> {
+    let contents = fs::read_to_string(manifest_file).map_err(|e| {
+        Error::new(
+            e.kind(),
+            format!("The run manifest {} could not be read: {}", manifest_file, e),
+        )
+    })?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Returns the runs of a manifest whose most recent attempt failed, in the order that they
+/// were first run. Runs are identified by their tool, working directory, and arguments.
+pub fn failed_runs(manifest_file: &str) -> Result<Vec<ManifestEntry>, Error> {
+    let entries = read_entries(manifest_file)?;
+    if entries.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("The run manifest {} contains no entries.", manifest_file),
+        ));
+    }
+    let mut order = vec![];
+    let mut latest: HashMap<(String, String, Vec<String>), ManifestEntry> = HashMap::new();
+    for entry in entries {
+        let key = (
+            entry.tool.to_lowercase(),
+            entry.working_directory.clone(),
+            entry.args.clone(),
+        );
+        if !latest.contains_key(&key) {
+            order.push(key.clone());
+        }
+        latest.insert(key, entry);
+    }
+    Ok(order
+        .into_iter()
+        .filter_map(|key| latest.remove(&key))
+        .filter(|entry| !entry.succeeded())
+        .collect())
+}
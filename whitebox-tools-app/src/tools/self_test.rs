@@ -0,0 +1,360 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: The self tests run tools on small synthetic datasets for which the correct output is
+known analytically, e.g. the slope of a plane or of a cone, and compare the tool outputs
+against those values. The datasets are generated in a temporary directory, so the tests
+need no data files. They run under `cargo test` and from the --selftest flag, so that an
+installed binary can be checked on the machine where it is used. Edge cells, where the
+tools' treatment of the missing neighbours differs from the analytical surface, are
+excluded from the comparisons.
+
+To add a test, write a function with the `SelfTestFn` signature that generates its inputs,
+runs the tool with `Harness::run`, and checks the output with `Harness::compare`, and add
+it to the list returned by `self_tests`.
+*/
+
+use super::terrain_analysis::{valley_elevation, valley_flow_accumulation};
+use super::{ConsoleProgress, ToolManager};
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::panic;
+use std::path;
+use std::process;
+use std::time::Instant;
+use whitebox_common::utils::get_formatted_elapsed_time;
+use whitebox_lidar::*;
+use whitebox_raster::*;
+
+/// The number of rows and columns in the synthetic rasters.
+const SIZE: isize = 101;
+/// The grid resolution of the synthetic rasters.
+const RES: f64 = 1.0;
+/// The coordinates of the north-west corner of the synthetic rasters. Projected coordinates are
+/// used so that the tools do not treat the data as geographic.
+const WEST: f64 = 500_000.0;
+const NORTH: f64 = 4_800_000.0;
+
+type SelfTestFn = fn(&Harness) -> Result<(), Error>;
+
+/// Returns the self tests, as (name, tool, test) triplets.
+fn self_tests() -> Vec<(&'static str, &'static str, SelfTestFn)> {
+    vec![
+        ("slope_of_plane", "Slope", slope_of_plane),
+        ("aspect_of_plane", "Aspect", aspect_of_plane),
+        ("slope_of_cone", "Slope", slope_of_cone),
+        ("slope_of_gaussian_hill", "Slope", slope_of_gaussian_hill),
+        ("d8_pointer_of_plane", "D8Pointer", d8_pointer_of_plane),
+        ("d8_flow_accumulation_of_valley", "D8FlowAccumulation", d8_flow_accumulation_of_valley),
+        ("mean_filter_of_plane", "MeanFilter", mean_filter_of_plane),
+        ("tin_gridding_of_planar_points", "LidarTINGridding", tin_gridding_of_planar_points),
+    ]
+}
+
+/// Runs the self tests whose name or tool contains `filter` (all tests if it is empty) and
+/// returns an error naming the tests that failed.
+pub(crate) fn run_self_tests(tm: &ToolManager, filter: &str) -> Result<(), Error> {
+    let filter = filter.trim().to_lowercase();
+    let tests: Vec<_> = self_tests()
+        .into_iter()
+        .filter(|(name, tool, _)| {
+            filter.is_empty()
+                || name.to_lowercase().contains(&filter)
+                || tool.to_lowercase().contains(&filter)
+        })
+        .collect();
+    if tests.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("No self tests match '{}'.", filter),
+        ));
+    }
+
+    let start = Instant::now();
+    let harness = Harness::new(tm)?;
+    let mut failed = vec![];
+    for (name, tool, test) in &tests {
+        // A panicking tool is reported as a failure rather than ending the test run.
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| test(&harness)))
+            .unwrap_or_else(|_| Err(Error::new(ErrorKind::Other, "the tool panicked")));
+        match result {
+            Ok(()) => println!("test {} ({}) ... ok", name, tool),
+            Err(e) => {
+                println!("test {} ({}) ... FAILED: {}", name, tool, e);
+                failed.push(*name);
+            }
+        }
+    }
+    let _ = fs::remove_dir_all(&harness.dir);
+
+    println!(
+        "\n{} passed; {} failed ({})",
+        tests.len() - failed.len(),
+        failed.len(),
+        get_formatted_elapsed_time(start)
+    );
+    if !failed.is_empty() {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("Self tests failed: {}", failed.join(", ")),
+        ));
+    }
+    Ok(())
+}
+
+/// Generates the synthetic datasets, runs tools on them, and compares their outputs.
+struct Harness<'a> {
+    tm: &'a ToolManager,
+    dir: String,
+}
+
+impl<'a> Harness<'a> {
+    fn new(tm: &'a ToolManager) -> Result<Harness<'a>, Error> {
+        let dir = env::temp_dir().join(format!("wbt_selftest_{}", process::id()));
+        fs::create_dir_all(&dir)?;
+        let mut dir = dir.to_string_lossy().to_string();
+        if !dir.ends_with(path::MAIN_SEPARATOR) {
+            dir.push(path::MAIN_SEPARATOR);
+        }
+        Ok(Harness { tm, dir })
+    }
+
+    /// Returns the coordinates of the centre of a cell of a synthetic raster, relative to the
+    /// centre of the raster.
+    fn cell_xy(row: isize, col: isize) -> (f64, f64) {
+        let centre = (SIZE / 2) as f64;
+        ((col as f64 - centre) * RES, (centre - row as f64) * RES)
+    }
+
+    /// Writes a raster in which each cell's value is `f(x, y)`, with x and y measured from the
+    /// centre of the raster, and returns its file name.
+    fn surface<F: Fn(f64, f64) -> f64>(&self, name: &str, f: F) -> Result<String, Error> {
+        let configs = RasterConfigs {
+            rows: SIZE as usize,
+            columns: SIZE as usize,
+            north: NORTH,
+            south: NORTH - SIZE as f64 * RES,
+            east: WEST + SIZE as f64 * RES,
+            west: WEST,
+            resolution_x: RES,
+            resolution_y: RES,
+            nodata: -32768.0,
+            data_type: DataType::F64,
+            photometric_interp: PhotometricInterpretation::Continuous,
+            ..Default::default()
+        };
+        let file_name = format!("{}.tif", name);
+        let mut output = Raster::initialize_using_config(&format!("{}{}", self.dir, file_name), &configs);
+        for row in 0..SIZE {
+            for col in 0..SIZE {
+                let (x, y) = Harness::cell_xy(row, col);
+                output.set_value(row, col, f(x, y));
+            }
+        }
+        output.write()?;
+        Ok(file_name)
+    }
+
+    /// Writes a LAS file of points sampled from `f(x, y)`, with x and y measured from the centre
+    /// of the synthetic rasters, and returns its file name. The points are placed on a jittered
+    /// grid with one point per cell, so that they do not coincide with cell centres.
+    fn point_cloud<F: Fn(f64, f64) -> f64>(&self, name: &str, f: F) -> Result<String, Error> {
+        let file_name = format!("{}.las", name);
+        let mut output = LasFile::new(&format!("{}{}", self.dir, file_name), "w")?;
+        let mut header: LasHeader = Default::default();
+        header.point_format = 0;
+        header.project_id_used = true;
+        header.x_scale_factor = 0.0001;
+        header.y_scale_factor = 0.0001;
+        header.z_scale_factor = 0.0001;
+        output.add_header(header);
+        output.header.x_offset = WEST;
+        output.header.y_offset = NORTH - SIZE as f64 * RES;
+        output.header.z_offset = 0.0;
+        let centre = SIZE as f64 * RES / 2.0;
+        for row in 0..SIZE {
+            for col in 0..SIZE {
+                // a deterministic offset in [0.1, 0.9) of a cell
+                let jitter = |a: isize, b: isize| 0.1 + 0.8 * ((a * 7919 + b * 104729) % 997) as f64 / 997.0;
+                let x = (col as f64 + jitter(row, col)) * RES;
+                let y = (row as f64 + jitter(col, row)) * RES;
+                let z = f(x - centre, y - centre);
+                let mut point_data: PointData = Default::default();
+                point_data.x = (x / output.header.x_scale_factor).round() as i32;
+                point_data.y = (y / output.header.y_scale_factor).round() as i32;
+                point_data.z = (z / output.header.z_scale_factor).round() as i32;
+                point_data.set_return_number(1);
+                point_data.set_number_of_returns(1);
+                output.add_point_record(LidarPointRecord::PointRecord0 { point_data });
+            }
+        }
+        output.write()?;
+        Ok(file_name)
+    }
+
+    /// Runs a tool in the test directory, without provenance or run manifest recording.
+    fn run(&self, tool_name: &str, args: &[&str]) -> Result<(), Error> {
+        let tool = self.tm.get_tool(tool_name).ok_or_else(|| {
+            Error::new(ErrorKind::NotFound, format!("Unrecognized tool name {}.", tool_name))
+        })?;
+        let args = args.iter().map(|a| a.to_string()).collect();
+        tool.run(args, &self.dir, false, &ConsoleProgress)
+    }
+
+    /// Compares the cells of an output raster that lie at least `margin` cells from its edges
+    /// against `expected(x, y)`, with x and y measured from the centre of the synthetic rasters,
+    /// and returns an error if any differs by more than `tolerance` or is NoData. Cells for which
+    /// `expected` returns None are skipped.
+    fn compare<F: Fn(f64, f64) -> Option<f64>>(
+        &self,
+        file_name: &str,
+        margin: isize,
+        tolerance: f64,
+        expected: F,
+    ) -> Result<(), Error> {
+        let output = Raster::new(&format!("{}{}", self.dir, file_name), "r")?;
+        let rows = output.configs.rows as isize;
+        let columns = output.configs.columns as isize;
+        let nodata = output.configs.nodata;
+        let centre_x = WEST + SIZE as f64 * RES / 2.0;
+        let centre_y = NORTH - SIZE as f64 * RES / 2.0;
+        let mut num_compared = 0;
+        let mut worst: Option<(f64, isize, isize, f64, f64)> = None;
+        for row in margin..rows - margin {
+            for col in margin..columns - margin {
+                let x = output.get_x_from_column(col) - centre_x;
+                let y = output.get_y_from_row(row) - centre_y;
+                if let Some(e) = expected(x, y) {
+                    let z = output.get_value(row, col);
+                    let diff = if is_nodata(z, nodata) { f64::INFINITY } else { (z - e).abs() };
+                    if worst.map_or(true, |w| diff > w.0) {
+                        worst = Some((diff, row, col, z, e));
+                    }
+                    num_compared += 1;
+                }
+            }
+        }
+        match worst {
+            None => Err(Error::new(
+                ErrorKind::Other,
+                format!("no cells of {} were compared", file_name),
+            )),
+            Some((diff, row, col, z, e)) if diff > tolerance => Err(Error::new(
+                ErrorKind::Other,
+                format!(
+                    "{} differs from the expected value by {:.6} at row {}, column {} (value {}, expected {}; tolerance {}; {} cells compared)",
+                    file_name, diff, row, col, z, e, tolerance, num_compared
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn slope_of_plane(h: &Harness) -> Result<(), Error> {
+    let (a, b) = (0.1f64, 0.2f64);
+    let dem = h.surface("plane", |x, y| 100.0 + a * x + b * y)?;
+    h.run("Slope", &[&format!("--dem={}", dem), "--output=plane_slope.tif"])?;
+    let slope = (a * a + b * b).sqrt().atan().to_degrees();
+    h.compare("plane_slope.tif", 2, 1e-3, |_, _| Some(slope))
+}
+
+fn aspect_of_plane(h: &Harness) -> Result<(), Error> {
+    let (a, b) = (0.1f64, 0.2f64);
+    let dem = h.surface("plane", |x, y| 100.0 + a * x + b * y)?;
+    h.run("Aspect", &[&format!("--dem={}", dem), "--output=plane_aspect.tif"])?;
+    // the azimuth of the downslope direction, clockwise from north
+    let aspect = (-a).atan2(-b).to_degrees().rem_euclid(360.0);
+    h.compare("plane_aspect.tif", 2, 1e-3, |_, _| Some(aspect))
+}
+
+fn slope_of_cone(h: &Harness) -> Result<(), Error> {
+    let k = 0.5f64;
+    let dem = h.surface("cone", |x, y| 100.0 - k * (x * x + y * y).sqrt())?;
+    h.run("Slope", &[&format!("--dem={}", dem), "--output=cone_slope.tif"])?;
+    let slope = k.atan().to_degrees();
+    // the surface is not differentiable at the apex
+    h.compare("cone_slope.tif", 2, 0.1, |x, y| {
+        if (x * x + y * y).sqrt() > 5.0 * RES {
+            Some(slope)
+        } else {
+            None
+        }
+    })
+}
+
+fn slope_of_gaussian_hill(h: &Harness) -> Result<(), Error> {
+    let (height, sigma) = (50f64, 15f64);
+    let dem = h.surface("gaussian_hill", |x, y| {
+        height * (-(x * x + y * y) / (2.0 * sigma * sigma)).exp()
+    })?;
+    h.run("Slope", &[&format!("--dem={}", dem), "--output=gaussian_hill_slope.tif"])?;
+    h.compare("gaussian_hill_slope.tif", 2, 0.1, |x, y| {
+        let r = (x * x + y * y).sqrt();
+        let gradient = height * r / (sigma * sigma) * (-(r * r) / (2.0 * sigma * sigma)).exp();
+        Some(gradient.atan().to_degrees())
+    })
+}
+
+fn d8_pointer_of_plane(h: &Harness) -> Result<(), Error> {
+    // The steepest descent from each cell is to its south-west neighbour, a drop of 0.3 over a
+    // distance of sqrt(2), compared with 0.2 to the south and 0.1 to the west.
+    let dem = h.surface("plane", |x, y| 100.0 + 0.1 * x + 0.2 * y)?;
+    h.run("D8Pointer", &[&format!("--dem={}", dem), "--output=plane_d8_pointer.tif"])?;
+    h.compare("plane_d8_pointer.tif", 1, 0.0, |_, _| Some(16.0))
+}
+
+fn d8_flow_accumulation_of_valley(h: &Harness) -> Result<(), Error> {
+    // The flanks of a synthetic valley drain across it to the axis, the middle column, which
+    // drains south to the outlet, such that the accumulation of each cell is known exactly.
+    let centre = (SIZE / 2) as f64;
+    let dem = h.surface("valley", |x, y| valley_elevation(x, y + centre * RES, 0.1, 0.01))?;
+    h.run(
+        "D8FlowAccumulation",
+        &[&format!("--input={}", dem), "--output=valley_accum.tif", "--out_type=cells"],
+    )?;
+    h.compare("valley_accum.tif", 1, 1e-6, |x, y| {
+        let row = (centre - y / RES).round() as isize;
+        let col = (centre + x / RES).round() as isize;
+        Some(valley_flow_accumulation(row, col, SIZE))
+    })
+}
+
+fn mean_filter_of_plane(h: &Harness) -> Result<(), Error> {
+    // the mean of a plane over a symmetric window is its value at the centre of the window
+    let plane = |x: f64, y: f64| 100.0 + 0.3 * x - 0.7 * y;
+    let dem = h.surface("plane2", plane)?;
+    h.run(
+        "MeanFilter",
+        &[&format!("--input={}", dem), "--output=plane2_mean.tif", "--filterx=5", "--filtery=5"],
+    )?;
+    h.compare("plane2_mean.tif", 2, 1e-3, |x, y| Some(plane(x, y)))
+}
+
+fn tin_gridding_of_planar_points(h: &Harness) -> Result<(), Error> {
+    // linear interpolation within the triangles of a TIN reproduces a plane exactly
+    let plane = |x: f64, y: f64| 10.0 + 0.3 * x - 0.1 * y;
+    let las = h.point_cloud("planar_points", plane)?;
+    h.run(
+        "LidarTINGridding",
+        &[&format!("--input={}", las), "--output=planar_points_tin.tif", "--resolution=1.0"],
+    )?;
+    h.compare("planar_points_tin.tif", 2, 1e-3, |x, y| Some(plane(x, y)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::run_self_tests;
+    use crate::tools::ToolManager;
+
+    #[test]
+    fn test_self_tests() {
+        let tm = ToolManager::new("", &false).unwrap();
+        run_self_tests(&tm, "").unwrap();
+    }
+}
@@ -0,0 +1,523 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: With the --server flag, the command-line program keeps running and accepts tool
+invocations over TCP connections, which spares interactive frontends (e.g. GIS plugins) the cost
+of starting the program, and of re-reading the same rasters, for every tool call. Clients send
+JSON-RPC 2.0 requests, one JSON object per line, and receive one response per request, also on a
+line of its own. The methods are:
+
+  run_tool         {"tool":"Slope","args":["-i=dem.tif","-o=slope.tif"],"working_directory":...}
+                   The args may also be given as an object, e.g. {"input":"dem.tif","zfactor":1.0},
+                   in which a value of true is passed as a flag and a value of false is omitted.
+                   Returns {"tool":...,"elapsed_seconds":...,"outputs":[...]}.
+  cancel           {"id":...} Cancels the run_tool request of this connection with the given id.
+  list_tools       Returns [{"name":...,"description":...}, ...].
+  tool_parameters  {"tool":"Slope"} Returns the parameters of a tool.
+  clear_cache      Empties the raster cache.
+  shutdown         Cancels any running tools and stops the server once they have stopped.
+
+While a tool runs, its progress is sent to the client as notifications, e.g.
+{"jsonrpc":"2.0","method":"progress","params":{"id":7,"label":"Progress","progress":45}}, where
+id is that of the run_tool request. Each run_tool request is run in its own thread, and so a
+client can cancel a run, or start others, while it is running. Tools are run in verbose mode, so
+that they report their progress, and their text output is printed by the server. A failed run is
+answered with an error whose code is -32000 and whose data give the cause of the failure (see the
+errors module of whitebox_common), e.g. {"kind":"grid_mismatch","exit_code":5,"message":...}.
+
+The settings in effect when the server starts, including any per-run flags given with --server,
+apply to all of its runs. Otherwise, runs share nothing but the raster cache: each uses its own
+working directory, which is passed to the tool rather than set for the process, and its own
+thread pool, and so concurrent runs don't affect one another. Rasters are kept in the raster cache between runs, and so the cache is
+enabled with a size of 1024 MB if the raster_cache_mb setting is zero. The server has no
+authentication and listens on the loopback interface by default; it should not be exposed to
+untrusted networks.
+*/
+
+use super::{get_name_and_description, CancellationToken, ProgressReporter, ToolManager};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Error, Write};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream};
+use std::panic;
+use std::path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use whitebox_common::errors::{error_json, ErrorCause};
+
+const VERSION: Option<&'static str> = option_env!("CARGO_PKG_VERSION");
+
+/// The address the server listens on when --server is given without one. Port 0 lets the
+/// operating system choose a free port, which is reported when the server starts.
+pub const DEFAULT_SERVER_ADDRESS: &str = "127.0.0.1:0";
+
+/// The size of the raster cache of a server whose raster_cache_mb setting is zero.
+const DEFAULT_CACHE_MB: usize = 1024;
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const TOOL_FAILED: i64 = -32000;
+
+struct RpcError {
+    code: i64,
+    message: String,
+    data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: &str) -> RpcError {
+        RpcError {
+            code,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+
+    fn tool_failed(cause: ErrorCause, message: &str) -> RpcError {
+        RpcError {
+            code: TOOL_FAILED,
+            message: message.to_string(),
+            data: Some(error_json(cause, message)),
+        }
+    }
+}
+
+/// The connection to a client, which is shared by the threads that answer its requests.
+#[derive(Clone)]
+struct Connection {
+    stream: Arc<Mutex<TcpStream>>,
+}
+
+impl Connection {
+    fn send(&self, message: Value) {
+        let mut stream = self.stream.lock().expect("Error writing to a client connection.");
+        // A client that has disconnected won't read its messages, and so write errors are ignored.
+        let _ = writeln!(stream, "{}", message);
+        let _ = stream.flush();
+    }
+
+    fn respond(&self, id: &Value, result: Result<Value, RpcError>) {
+        match result {
+            Ok(result) => self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+            Err(e) => {
+                let mut error = json!({ "code": e.code, "message": e.message });
+                if let Some(data) = e.data {
+                    error["data"] = data;
+                }
+                self.send(json!({ "jsonrpc": "2.0", "id": id, "error": error }));
+            }
+        }
+    }
+}
+
+/// Sends the progress of a run to the client as notifications.
+struct RpcProgress {
+    id: Value,
+    connection: Connection,
+}
+
+impl ProgressReporter for RpcProgress {
+    fn progress(&self, label: &str, percent: usize) {
+        self.connection.send(json!({
+            "jsonrpc": "2.0",
+            "method": "progress",
+            "params": { "id": self.id, "label": label, "progress": percent },
+        }));
+    }
+}
+
+struct Server {
+    working_directory: String,
+    address: SocketAddr,
+    shutdown: AtomicBool,
+    // the cancellation tokens of the running tools, keyed by connection and request id
+    runs: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl Server {
+    fn run_count(&self) -> usize {
+        self.runs.lock().expect("Error reading the running tools.").len()
+    }
+
+    /// Stops accepting connections, by waking the listener after setting the shutdown flag.
+    fn stop(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        let mut address = self.address;
+        if address.ip().is_unspecified() {
+            address.set_ip(match address {
+                SocketAddr::V4(_) => Ipv4Addr::LOCALHOST.into(),
+                SocketAddr::V6(_) => Ipv6Addr::LOCALHOST.into(),
+            });
+        }
+        let _ = TcpStream::connect(address);
+    }
+}
+
+/// Runs the server until a client asks it to shut down. Runs use `working_directory` unless a
+/// request gives its own.
+pub(crate) fn run_server(working_directory: &str, address: &str) -> Result<(), Error> {
+    let mut configs = whitebox_common::configs::get_configs()?;
+    if configs.raster_cache_mb == 0 {
+        configs.raster_cache_mb = DEFAULT_CACHE_MB;
+    }
+    whitebox_common::configs::set_run_configs(&configs);
+
+    let (server, listener) = bind(working_directory, address)?;
+    // The first line of output gives the address, which clients need when port 0 was used.
+    println!(
+        "{}",
+        json!({ "listening": server.address.to_string(), "version": VERSION.unwrap_or("unknown") })
+    );
+    accept_connections(&server, listener);
+    Ok(())
+}
+
+fn bind(working_directory: &str, address: &str) -> Result<(Arc<Server>, TcpListener), Error> {
+    let listener = TcpListener::bind(address).map_err(|e| {
+        Error::new(e.kind(), format!("The server could not listen on {}: {}", address, e))
+    })?;
+    let server = Arc::new(Server {
+        working_directory: working_directory.to_string(),
+        address: listener.local_addr()?,
+        shutdown: AtomicBool::new(false),
+        runs: Mutex::new(HashMap::new()),
+    });
+    Ok((server, listener))
+}
+
+/// Serves the clients that connect to `listener` until one asks the server to shut down, and
+/// then waits for the running tools to stop.
+fn accept_connections(server: &Arc<Server>, listener: TcpListener) {
+    for (client, stream) in listener.incoming().enumerate() {
+        if server.shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                let server = server.clone();
+                thread::spawn(move || serve_connection(&server, stream, client));
+            }
+            Err(e) => println!("Warning: a client connection failed: {}", e),
+        }
+    }
+
+    for token in server.runs.lock().expect("Error reading the running tools.").values() {
+        token.cancel();
+    }
+    while server.run_count() > 0 {
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Answers the requests of a client until it disconnects.
+fn serve_connection(server: &Arc<Server>, stream: TcpStream, client: usize) {
+    let connection = match stream.try_clone() {
+        Ok(s) => Connection {
+            stream: Arc::new(Mutex::new(s)),
+        },
+        Err(e) => {
+            println!("Warning: a client connection failed: {}", e);
+            return;
+        }
+    };
+    for line in BufReader::new(stream).lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(_) => break,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: Value = match serde_json::from_str(&line) {
+            Ok(r) => r,
+            Err(e) => {
+                connection.respond(&Value::Null, Err(RpcError::new(PARSE_ERROR, &format!("Parse error: {}", e))));
+                continue;
+            }
+        };
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let params = request.get("params").cloned().unwrap_or(json!({}));
+        let method = match request["method"].as_str() {
+            Some(m) => m.to_string(),
+            None => {
+                connection.respond(&id, Err(RpcError::new(INVALID_REQUEST, "The request has no method.")));
+                continue;
+            }
+        };
+        match method.as_str() {
+            "run_tool" => {
+                let key = format!("{}#{}", client, id);
+                let token = CancellationToken::new();
+                let mut runs = server.runs.lock().expect("Error reading the running tools.");
+                if runs.contains_key(&key) {
+                    connection.respond(
+                        &id,
+                        Err(RpcError::new(INVALID_REQUEST, &format!("A tool is already running with the id {}.", id))),
+                    );
+                    continue;
+                }
+                runs.insert(key.clone(), token.clone());
+                drop(runs);
+                let (server, connection) = (server.clone(), connection.clone());
+                thread::spawn(move || {
+                    let result = run_tool(&server, &connection, &id, &params, token);
+                    server.runs.lock().expect("Error reading the running tools.").remove(&key);
+                    connection.respond(&id, result);
+                });
+            }
+            "cancel" => {
+                let key = format!("{}#{}", client, params["id"]);
+                let cancelled = match server.runs.lock().expect("Error reading the running tools.").get(&key) {
+                    Some(token) => {
+                        token.cancel();
+                        true
+                    }
+                    None => false,
+                };
+                connection.respond(&id, Ok(json!(cancelled)));
+            }
+            "list_tools" => connection.respond(&id, list_tools(server)),
+            "tool_parameters" => connection.respond(&id, tool_parameters(server, &params)),
+            "clear_cache" => {
+                whitebox_raster::clear_raster_cache();
+                connection.respond(&id, Ok(Value::Null));
+            }
+            "shutdown" => {
+                connection.respond(&id, Ok(Value::Null));
+                server.stop();
+                break;
+            }
+            _ => connection.respond(
+                &id,
+                Err(RpcError::new(METHOD_NOT_FOUND, &format!("Unrecognized method {}.", method))),
+            ),
+        }
+    }
+}
+
+/// Runs a tool, in verbose mode, and returns the result of a successful run.
+fn run_tool(
+    server: &Server,
+    connection: &Connection,
+    id: &Value,
+    params: &Value,
+    token: CancellationToken,
+) -> Result<Value, RpcError> {
+    let tool_name = params["tool"]
+        .as_str()
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "The tool to run must be given, e.g. {\"tool\":\"Slope\"}."))?
+        .to_string();
+    let args = tool_args(&params["args"])?;
+    let mut working_directory = params["working_directory"]
+        .as_str()
+        .unwrap_or(&server.working_directory)
+        .to_string();
+    if !working_directory.is_empty() && !working_directory.ends_with(path::MAIN_SEPARATOR) {
+        working_directory.push(path::MAIN_SEPARATOR);
+    }
+    let mut tm = ToolManager::new(&working_directory, &true)
+        .map_err(|e| RpcError::tool_failed(ErrorCause::of(&e), &e.to_string()))?;
+    tm.set_cancellation_token(token);
+    let reporter = RpcProgress {
+        id: id.clone(),
+        connection: connection.clone(),
+    };
+
+    let start = Instant::now();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        tm.run_tool_with_progress(tool_name.clone(), args.clone(), &reporter)
+    }));
+    match result {
+        Ok(Ok(())) => Ok(json!({
+            "tool": tool_name,
+            "elapsed_seconds": start.elapsed().as_secs_f64(),
+            "outputs": tm.existing_outputs(&tool_name, &args),
+        })),
+        Ok(Err(e)) => Err(RpcError::tool_failed(ErrorCause::of(&e), &e.to_string())),
+        Err(payload) => {
+            // the message of the panic has already been printed
+            let message = payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or(String::from("The tool panicked."));
+            Err(RpcError::tool_failed(ErrorCause::Other, &message))
+        }
+    }
+}
+
+/// Converts the args of a run_tool request, either an array of command-line arguments or an
+/// object of parameter flags and values, into command-line arguments.
+fn tool_args(args: &Value) -> Result<Vec<String>, RpcError> {
+    match args {
+        Value::Null => Ok(vec![]),
+        Value::Array(a) => a
+            .iter()
+            .map(|v| match v {
+                Value::String(s) => Ok(s.clone()),
+                _ => Err(RpcError::new(INVALID_PARAMS, "The args of a tool must be strings.")),
+            })
+            .collect(),
+        Value::Object(o) => {
+            let mut args = vec![];
+            for (flag, value) in o {
+                let flag = format!("--{}", flag.trim_start_matches('-'));
+                match value {
+                    Value::Bool(true) => args.push(flag),
+                    Value::Bool(false) | Value::Null => {}
+                    Value::String(s) => args.push(format!("{}={}", flag, s)),
+                    Value::Array(a) => {
+                        // lists of files are separated by semicolons, as they are on the command line
+                        let items: Vec<String> = a
+                            .iter()
+                            .map(|v| v.as_str().map(|s| s.to_string()).unwrap_or(v.to_string()))
+                            .collect();
+                        args.push(format!("{}={}", flag, items.join(";")));
+                    }
+                    _ => args.push(format!("{}={}", flag, value)),
+                }
+            }
+            Ok(args)
+        }
+        _ => Err(RpcError::new(
+            INVALID_PARAMS,
+            "The args of a tool must be an array of arguments or an object of parameter values.",
+        )),
+    }
+}
+
+fn list_tools(server: &Server) -> Result<Value, RpcError> {
+    let tm = ToolManager::new(&server.working_directory, &false)
+        .map_err(|e| RpcError::tool_failed(ErrorCause::of(&e), &e.to_string()))?;
+    let tools: Vec<Value> = tm
+        .tool_names
+        .iter()
+        .filter_map(|name| tm.get_tool(name))
+        .map(|tool| {
+            let (name, description) = get_name_and_description(tool);
+            json!({ "name": name, "description": description })
+        })
+        .collect();
+    Ok(Value::Array(tools))
+}
+
+fn tool_parameters(server: &Server, params: &Value) -> Result<Value, RpcError> {
+    let tool_name = params["tool"]
+        .as_str()
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "The tool must be given, e.g. {\"tool\":\"Slope\"}."))?;
+    let tm = ToolManager::new(&server.working_directory, &false)
+        .map_err(|e| RpcError::tool_failed(ErrorCause::of(&e), &e.to_string()))?;
+    let tool = tm.get_tool(tool_name).ok_or_else(|| {
+        RpcError::tool_failed(ErrorCause::InvalidParameter, &format!("Unrecognized tool name {}.", tool_name))
+    })?;
+    serde_json::from_str(&tool.get_tool_parameters())
+        .map_err(|e| RpcError::tool_failed(ErrorCause::Other, &e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{accept_connections, bind, tool_args};
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    #[test]
+    fn test_tool_args() {
+        let args = tool_args(&json!(["-i=dem.tif", "--output=slope.tif"])).ok().unwrap();
+        assert_eq!(args, vec!["-i=dem.tif", "--output=slope.tif"]);
+        let args = tool_args(&json!({
+            "input": "dem.tif",
+            "zfactor": 2.5,
+            "inputs": ["a.tif", "b.tif"],
+            "log": true,
+            "units": false
+        }))
+        .ok()
+        .unwrap();
+        assert_eq!(args, vec!["--input=dem.tif", "--inputs=a.tif;b.tif", "--log", "--zfactor=2.5"]);
+        assert!(tool_args(&json!([1, 2])).is_err());
+        assert!(tool_args(&json!("dem.tif")).is_err());
+    }
+
+    #[test]
+    fn test_concurrent_runs() {
+        let (server, listener) = bind("", "127.0.0.1:0").unwrap();
+        let address = server.address;
+        let server_thread = thread::spawn(move || accept_connections(&server, listener));
+
+        // each run reads and writes files of the same names, relative to its own working directory
+        let dirs: Vec<_> = (0..2)
+            .map(|i| std::env::temp_dir().join(format!("wbt_server_{}_{}", std::process::id(), i)))
+            .collect();
+        for (i, dir) in dirs.iter().enumerate() {
+            fs::create_dir_all(dir).unwrap();
+            let value = -(i as f64 + 1.0);
+            fs::write(
+                dir.join("dem.asc"),
+                format!("ncols 2\nnrows 2\nxllcorner 0\nyllcorner 0\ncellsize 1\nNODATA_value -9999\n{0} {0}\n{0} {0}\n", value),
+            )
+            .unwrap();
+        }
+        let clients: Vec<_> = dirs
+            .iter()
+            .enumerate()
+            .map(|(i, dir)| {
+                let request = json!({
+                    "jsonrpc": "2.0",
+                    "id": i,
+                    "method": "run_tool",
+                    "params": {
+                        "tool": "AbsoluteValue",
+                        "args": ["-i=dem.asc", "-o=abs.asc"],
+                        "working_directory": dir.to_string_lossy(),
+                    },
+                });
+                thread::spawn(move || {
+                    let mut stream = TcpStream::connect(address).unwrap();
+                    writeln!(stream, "{}", request).unwrap();
+                    // skip the progress notifications, which have no id
+                    for line in BufReader::new(stream).lines() {
+                        let message: Value = serde_json::from_str(&line.unwrap()).unwrap();
+                        if message.get("id").is_some() {
+                            return message;
+                        }
+                    }
+                    panic!("The server closed the connection without responding.");
+                })
+            })
+            .collect();
+
+        for (i, client) in clients.into_iter().enumerate() {
+            let response = client.join().unwrap();
+            assert_eq!(response["id"], json!(i));
+            assert!(response["result"].is_object(), "{}", response);
+            let output = fs::read_to_string(dirs[i].join("abs.asc")).unwrap();
+            let last_row: Vec<f64> = output
+                .lines()
+                .last()
+                .unwrap()
+                .split_whitespace()
+                .map(|v| v.parse().unwrap())
+                .collect();
+            assert_eq!(last_row, vec![i as f64 + 1.0; 2]);
+        }
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        writeln!(stream, "{}", json!({ "jsonrpc": "2.0", "id": 0, "method": "shutdown" })).unwrap();
+        server_thread.join().unwrap();
+        for dir in dirs {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
@@ -0,0 +1,518 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use whitebox_common::structures::Array2D;
+use crate::tools::*;
+use whitebox_vector::{ShapeType, Shapefile};
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool adjusts a raster stream network (`--streams`), typically extracted from a D8 flow-accumulation
+/// raster, so that it follows the mapped positions of streams contained in a vector hydrography layer
+/// (`--hydrography`). DEM-derived streams often depart from the true channel positions in areas of low
+/// relief, where small elevation errors can displace the modelled flow paths by tens or hundreds of metres,
+/// and watersheds delineated from pour points on these streams can be badly misdelineated as a result.
+///
+/// The correction is restricted to a search corridor. Each grid cell crossed by a mapped stream line is
+/// considered to be part of the corrected network if there is at least one raster stream cell within the
+/// corridor distance (`--distance`, in map units) of it, i.e. where the two representations of the network
+/// describe the same stream; mapped streams with no nearby raster streams, such as channels upstream of
+/// the raster channel heads, are ignored. Raster stream cells lying within the corridor of the corrected
+/// mapped streams are then replaced by the mapped stream cells, while raster stream cells outside of the
+/// corridor, e.g. unmapped headwater streams, are retained. Finally, each retained raster stream that enters
+/// the corridor is reconnected to the nearest mapped stream cell by a straight run of stream cells, so that
+/// the output network remains connected. The output stream cells are assigned a value of 1 and the
+/// background value of the input (either zero or nodata) is preserved. The corridor distance defaults to
+/// five grid cells.
+///
+/// Optionally, a vector of pour points (`--pour_pts`) may be relocated to the corrected network. Each pour
+/// point is moved to the centre of the nearest output stream cell within the snap distance (`--snap_dist`,
+/// in map units, which defaults to the corridor distance), and the relocated points are written to an
+/// output vector file (`--out_pour_pts`) with the attributes of the input points. Pour points with no
+/// stream cell within the snap distance are not moved.
+///
+/// Because the corrected stream network no longer necessarily coincides with the D8 flow paths of the DEM,
+/// the network is intended to be used for stream burning (e.g. with `FillBurn` or `BurnStreamsAtRoads`)
+/// before re-deriving the flow directions and delineating watersheds.
+///
+/// # See Also
+/// `RasterizeStreams`, `FillBurn`, `JensonSnapPourPoints`, `SnapPourPoints`, `ExtractStreams`
+pub struct ConformStreamsToHydrography {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ConformStreamsToHydrography {
+    pub fn new() -> ConformStreamsToHydrography {
+        // public constructor
+        let name = "ConformStreamsToHydrography".to_string();
+        let toolbox = "Stream Network Analysis".to_string();
+        let description = "Adjusts a raster stream network to follow mapped vector hydrography within a search corridor.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Raster Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Vector Hydrography File".to_owned(),
+            flags: vec!["--hydrography".to_owned()],
+            description: "Input vector stream lines file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster streams file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Corridor Distance (optional)".to_owned(),
+            flags: vec!["--distance".to_owned()],
+            description: "Search corridor distance, in map units; defaults to five grid cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Pour Points File (optional)".to_owned(),
+            flags: vec!["--pour_pts".to_owned()],
+            description: "Input vector pour points file to relocate to the corrected network.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Pour Points File (optional)".to_owned(),
+            flags: vec!["--out_pour_pts".to_owned()],
+            description: "Output vector file of relocated pour points.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Point,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Snap Distance (optional)".to_owned(),
+            flags: vec!["--snap_dist".to_owned()],
+            description: "Maximum pour point relocation distance, in map units; defaults to the corridor distance.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --streams=streams.tif --hydrography=nhd_flowlines.shp -o=corrected_streams.tif --distance=50.0 --pour_pts=gauges.shp --out_pour_pts=gauges_snapped.shp", short_exe, name).replace("*", &sep);
+
+        ConformStreamsToHydrography {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ConformStreamsToHydrography {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut hydrography_file = parsed.get_string("hydrography").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut distance = parsed.get_f64("distance").unwrap_or(0f64);
+        let mut pourpts_file = parsed.get_string("pour_pts").unwrap_or_default();
+        let mut out_pourpts_file = parsed.get_string("out_pour_pts").unwrap_or_default();
+        let mut snap_dist = parsed.get_f64("snap_dist").unwrap_or(0f64);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        hydrography_file = resolve_input_path(&hydrography_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        if !pourpts_file.is_empty() {
+            pourpts_file = resolve_input_path(&pourpts_file, working_directory)?;
+            if out_pourpts_file.is_empty() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "An output pour points file (--out_pour_pts) must be specified when pour points are input.",
+                ));
+            }
+            out_pourpts_file = resolve_path(&out_pourpts_file, working_directory);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let hydrography = Shapefile::read(&hydrography_file)?;
+        if hydrography.header.shape_type.base_shape_type() != ShapeType::PolyLine {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input hydrography vector data must be of polyline base shape type.",
+            ));
+        }
+        let streams = Raster::new(&streams_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = streams.configs.rows as isize;
+        let columns = streams.configs.columns as isize;
+        let nodata = streams.configs.nodata;
+        let res = (streams.configs.resolution_x + streams.configs.resolution_y) / 2f64;
+        if distance <= 0f64 {
+            distance = 5f64 * res;
+        }
+        if snap_dist <= 0f64 {
+            snap_dist = distance;
+        }
+
+        let mut background = nodata;
+        let mut z: f64;
+        let mut is_stream: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                z = streams.get_value(row, col);
+                if z == 0f64 {
+                    background = 0f64;
+                } else if z > 0f64 && !is_nodata(z, nodata) {
+                    is_stream.set_value(row, col, 1u8);
+                }
+            }
+        }
+
+        // offsets of the cells within the corridor distance, sorted by distance
+        let radius = (distance / res).ceil() as isize;
+        let mut corridor_offsets = vec![];
+        for dr in -radius..=radius {
+            for dc in -radius..=radius {
+                let d = ((dr * dr + dc * dc) as f64).sqrt() * res;
+                if d <= distance {
+                    corridor_offsets.push((dr, dc, d));
+                }
+            }
+        }
+        corridor_offsets.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
+
+        // Rasterize the mapped streams. Sampling each segment at a quarter of the
+        // cell size produces an 8-connected trace of cells.
+        if verbose {
+            println!("Rasterizing hydrography...");
+        }
+        let step = streams.configs.resolution_x.min(streams.configs.resolution_y) / 4f64;
+        let mut mapped_cells = vec![];
+        let mut is_mapped: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        for record_num in 0..hydrography.num_records {
+            reporter.check_cancelled()?;
+            let record = hydrography.get_record(record_num);
+            for part in 0..record.num_parts as usize {
+                let part_start = record.parts[part] as usize;
+                let part_end = if part < record.num_parts as usize - 1 {
+                    record.parts[part + 1] as usize
+                } else {
+                    record.num_points as usize
+                };
+                for i in part_start..part_end {
+                    let (p1, p2) = if i + 1 < part_end {
+                        (record.points[i], record.points[i + 1])
+                    } else {
+                        (record.points[i], record.points[i])
+                    };
+                    let num_steps = (p1.distance(&p2) / step).ceil().max(1f64) as usize;
+                    for s in 0..=num_steps {
+                        let t = s as f64 / num_steps as f64;
+                        let row = streams.get_row_from_y(p1.y + t * (p2.y - p1.y));
+                        let col = streams.get_column_from_x(p1.x + t * (p2.x - p1.x));
+                        if row >= 0 && row < rows && col >= 0 && col < columns && is_mapped.get_value(row, col) == 0 {
+                            is_mapped.set_value(row, col, 1u8);
+                            mapped_cells.push((row, col));
+                        }
+                    }
+                }
+            }
+        }
+
+        // Keep the mapped cells that have a raster stream within the corridor, and mark
+        // the corridor around them.
+        if verbose {
+            println!("Matching the mapped and raster streams...");
+        }
+        let mut in_corridor: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let mut corrected_cells = vec![];
+        for (i, &(row, col)) in mapped_cells.iter().enumerate() {
+            let matched = corridor_offsets.iter().any(|&(dr, dc, _)| {
+                is_stream.get_value(row + dr, col + dc) == 1
+            });
+            if matched {
+                corrected_cells.push((row, col));
+                for &(dr, dc, _) in &corridor_offsets {
+                    let (rn, cn) = (row + dr, col + dc);
+                    if rn >= 0 && rn < rows && cn >= 0 && cn < columns {
+                        in_corridor.set_value(rn, cn, 1u8);
+                    }
+                }
+            } else {
+                is_mapped.set_value(row, col, 0u8);
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * i as f64 / (mapped_cells.len() - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Build the corrected network.
+        let mut output = Raster::initialize_using_file(&output_file, &streams);
+        output.configs.data_type = DataType::I16;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        if background != 0f64 {
+            output.configs.nodata = -32768f64;
+            background = -32768f64;
+        }
+        output.reinitialize_values(background);
+        let mut num_replaced = 0usize;
+        let mut num_retained = 0usize;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                if is_stream.get_value(row, col) == 1 {
+                    if in_corridor.get_value(row, col) == 0 {
+                        output.set_value(row, col, 1f64);
+                        num_retained += 1;
+                    } else {
+                        num_replaced += 1;
+                    }
+                }
+            }
+        }
+        for &(row, col) in &corrected_cells {
+            output.set_value(row, col, 1f64);
+        }
+
+        // Reconnect the retained raster streams that enter the corridor to the nearest
+        // corrected stream cell.
+        let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+        let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let reconnect_radius = radius + 2;
+        let mut reconnect_offsets = vec![];
+        for dr in -reconnect_radius..=reconnect_radius {
+            for dc in -reconnect_radius..=reconnect_radius {
+                reconnect_offsets.push((dr, dc, dr * dr + dc * dc));
+            }
+        }
+        reconnect_offsets.sort_by_key(|a| a.2);
+        let mut num_connections = 0usize;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                if is_stream.get_value(row, col) != 1 || in_corridor.get_value(row, col) == 1 {
+                    continue;
+                }
+                let enters_corridor = (0..8).any(|n| {
+                    let (rn, cn) = (row + d_y[n], col + d_x[n]);
+                    is_stream.get_value(rn, cn) == 1 && in_corridor.get_value(rn, cn) == 1
+                });
+                if !enters_corridor {
+                    continue;
+                }
+                let target = reconnect_offsets
+                    .iter()
+                    .map(|&(dr, dc, _)| (row + dr, col + dc))
+                    .find(|&(rn, cn)| is_mapped.get_value(rn, cn) == 1);
+                if let Some((tr, tc)) = target {
+                    // a straight, 8-connected run of cells to the target
+                    let num_steps = (tr - row).abs().max((tc - col).abs());
+                    for s in 1..=num_steps {
+                        let t = s as f64 / num_steps as f64;
+                        let r = row + ((tr - row) as f64 * t).round() as isize;
+                        let c = col + ((tc - col) as f64 * t).round() as isize;
+                        output.set_value(r, c, 1f64);
+                    }
+                    num_connections += 1;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input streams file: {}", streams_file));
+        output.add_metadata_entry(format!("Input hydrography file: {}", hydrography_file));
+        output.add_metadata_entry(format!("Corridor distance: {}", distance));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Raster stream cells retained: {}", num_retained);
+            println!("Raster stream cells replaced: {}", num_replaced);
+            println!("Mapped stream cells added: {}", corrected_cells.len());
+            println!("Reconnected streams: {}", num_connections);
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !pourpts_file.is_empty() {
+            let pourpts = Shapefile::read(&pourpts_file)?;
+            if pourpts.header.shape_type.base_shape_type() != ShapeType::Point {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input pour points vector data must be of point base shape type.",
+                ));
+            }
+            let mut out_pourpts =
+                Shapefile::initialize_using_file(&out_pourpts_file, &pourpts, ShapeType::Point, true)?;
+            let snap_radius = (snap_dist / res).ceil() as isize;
+            let mut num_moved = 0usize;
+            for record_num in 0..pourpts.num_records {
+                reporter.check_cancelled()?;
+                let record = pourpts.get_record(record_num);
+                let attr_rec = pourpts.attributes.get_record(record_num);
+                out_pourpts
+                    .attributes
+                    .add_record(attr_rec, pourpts.attributes.is_deleted[record_num]);
+                let (x, y) = (record.points[0].x, record.points[0].y);
+                let row = output.get_row_from_y(y);
+                let col = output.get_column_from_x(x);
+                let mut min_dist = f64::INFINITY;
+                let (mut xn, mut yn) = (x, y);
+                for r in (row - snap_radius)..=(row + snap_radius) {
+                    for c in (col - snap_radius)..=(col + snap_radius) {
+                        if output.get_value(r, c) == 1f64 {
+                            let xc = output.get_x_from_column(c);
+                            let yc = output.get_y_from_row(r);
+                            let dist = ((xc - x) * (xc - x) + (yc - y) * (yc - y)).sqrt();
+                            if dist <= snap_dist && dist < min_dist {
+                                min_dist = dist;
+                                xn = xc;
+                                yn = yc;
+                            }
+                        }
+                    }
+                }
+                if min_dist.is_finite() {
+                    num_moved += 1;
+                }
+                out_pourpts.add_point_record(xn, yn);
+            }
+
+            let _ = match out_pourpts.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output pour points file written");
+                        println!(
+                            "Pour points relocated: {} of {}",
+                            num_moved, pourpts.num_records
+                        );
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
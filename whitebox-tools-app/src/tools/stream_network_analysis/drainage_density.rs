@@ -0,0 +1,599 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_common::algorithms::point_in_poly;
+use whitebox_raster::*;
+use whitebox_common::structures::{Array2D, Point2D};
+use crate::tools::*;
+use whitebox_vector::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool calculates the drainage density, i.e. the total length of stream channels per unit area, and the
+/// source density, i.e. the number of channel heads per unit area, of a stream network. The user must specify
+/// the name of a raster containing streams data (`--streams`), where stream grid cells are denoted by all positive
+/// non-zero values, and a D8 flow pointer (i.e. flow direction) raster (`--d8_pntr`). The pointer image is used to
+/// measure the length of channel within each stream cell and to identify channel heads, i.e. stream cells with
+/// no inflowing stream cells. By default, the pointer raster is assumed to use the clockwise indexing method used by
+/// WhiteboxTools. If the pointer file contains ESRI flow direction values instead, the `--esri_pntr` parameter must
+/// be specified.
+///
+/// Densities may be calculated in either of two ways. By default, the tool calculates the densities within a
+/// square moving window centred on each grid cell, of size `--filter` grid cells. Only grid cells with valid
+/// pointer values contribute to the window area, and so densities near the edges of the data are not
+/// underestimated. Alternatively, if a vector polygon file of watersheds (`--watersheds`) is specified, the
+/// densities are calculated for each watershed polygon and every grid cell within a watershed is assigned the
+/// values of its watershed. In this case, the user may also optionally specify an output vector (`--out_basins`),
+/// which is a copy of the watersheds with the added attributes STRM_LEN (total channel length), NUM_SRCS (number
+/// of channel heads), AREA (the area of the watershed's grid cells), DRAIN_DEN (drainage density), and SRC_DEN
+/// (source density).
+///
+/// The output drainage density raster (`--output`) is measured in units of map units<sup>-1</sup> (e.g. m/m<sup>2</sup>;
+/// multiply by 1000 to convert to km/km<sup>2</sup>), and the optional source density raster (`--out_sources`)
+/// is measured in channel heads per map unit<sup>2</sup> (multiply by 10<sup>6</sup> to convert to channel heads
+/// per km<sup>2</sup>). The input rasters should be in a projected coordinate system.
+///
+/// # See Also
+/// `LengthOfUpstreamChannels`, `ExtractStreams`, `D8Pointer`, `Watershed`
+pub struct DrainageDensity {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl DrainageDensity {
+    pub fn new() -> DrainageDensity {
+        // public constructor
+        let name = "DrainageDensity".to_string();
+        let toolbox = "Stream Network Analysis".to_string();
+        let description = "Calculates drainage density and channel-head (source) density within a moving window or watersheds.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input D8 Pointer File".to_owned(),
+            flags: vec!["--d8_pntr".to_owned()],
+            description: "Input raster D8 pointer file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Watersheds File (optional)".to_owned(),
+            flags: vec!["--watersheds".to_owned()],
+            description: "Input vector watershed polygons file; if unspecified, a moving window is used.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Drainage Density File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output drainage density raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Source Density File (optional)".to_owned(),
+            flags: vec!["--out_sources".to_owned()],
+            description: "Output channel-head (source) density raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Basins File (optional)".to_owned(),
+            flags: vec!["--out_basins".to_owned()],
+            description: "Output vector watersheds file with per-basin density attributes.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Filter Size (cells)".to_owned(),
+            flags: vec!["--filter".to_owned()],
+            description: "Size of the moving window, in grid cells; ignored when watersheds are specified.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("101".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
+            description: "D8 pointer uses the ESRI style scheme.".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --d8_pntr=D8.tif --streams=streams.tif -o=drainage_density.tif --out_sources=source_density.tif --filter=101
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --d8_pntr=D8.tif --streams=streams.tif --watersheds=basins.shp -o=drainage_density.tif --out_basins=basin_densities.shp --esri_pntr", short_exe, name).replace("*", &sep);
+
+        DrainageDensity {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for DrainageDensity {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("d8_pntr").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut watersheds_file = parsed.get_string("watersheds").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut sources_file = parsed.get_string("out_sources").unwrap_or_default();
+        let mut basins_file = parsed.get_string("out_basins").unwrap_or_default();
+        let mut filter_size = parsed.get_i64("filter").map(|v| v as isize).unwrap_or(101isize);
+        let esri_style = parsed.get_bool("esri_pntr");
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        for file in [
+            &mut d8_file,
+            &mut streams_file,
+            &mut watersheds_file,
+            &mut output_file,
+            &mut sources_file,
+            &mut basins_file,
+        ] {
+            *file = resolve_path(file, working_directory);
+        }
+        if !basins_file.is_empty() && watersheds_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "An output basins file (--out_basins) can only be created when watersheds (--watersheds) are specified.",
+            ));
+        }
+        if filter_size < 3 {
+            filter_size = 3;
+        }
+        if filter_size % 2 == 0 {
+            filter_size += 1;
+        }
+
+        if verbose {
+            println!("Reading pointer data...")
+        };
+        let pntr = Raster::new(&d8_file, "r")?;
+        if verbose {
+            println!("Reading streams data...")
+        };
+        let streams = Raster::new(&streams_file, "r")?;
+
+        let start = Instant::now();
+
+        let rows = pntr.configs.rows as isize;
+        let columns = pntr.configs.columns as isize;
+        let pntr_nodata = pntr.configs.nodata;
+        let streams_nodata = streams.configs.nodata;
+        let cell_size_x = pntr.configs.resolution_x;
+        let cell_size_y = pntr.configs.resolution_y;
+        let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+        let cell_area = cell_size_x * cell_size_y;
+
+        // make sure the input files have the same size
+        if streams.configs.rows != pntr.configs.rows
+            || streams.configs.columns != pntr.configs.columns
+        {
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
+        }
+        warn_if_crs_differs(&streams, &pntr, verbose);
+
+        // Create a mapping from the pointer values to cells offsets.
+        let mut pntr_matches: [usize; 129] = [999usize; 129];
+        if !esri_style {
+            pntr_matches[1] = 0usize;
+            pntr_matches[2] = 1usize;
+            pntr_matches[4] = 2usize;
+            pntr_matches[8] = 3usize;
+            pntr_matches[16] = 4usize;
+            pntr_matches[32] = 5usize;
+            pntr_matches[64] = 6usize;
+            pntr_matches[128] = 7usize;
+        } else {
+            pntr_matches[1] = 1usize;
+            pntr_matches[2] = 2usize;
+            pntr_matches[4] = 3usize;
+            pntr_matches[8] = 4usize;
+            pntr_matches[16] = 5usize;
+            pntr_matches[32] = 6usize;
+            pntr_matches[64] = 7usize;
+            pntr_matches[128] = 0usize;
+        }
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let grid_lengths = [
+            diag_cell_size,
+            cell_size_x,
+            diag_cell_size,
+            cell_size_y,
+            diag_cell_size,
+            cell_size_x,
+            diag_cell_size,
+            cell_size_y,
+        ];
+        let mut inflowing_vals = [16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64, 8f64];
+        if esri_style {
+            inflowing_vals = [8f64, 16f64, 32f64, 64f64, 128f64, 1f64, 2f64, 4f64];
+        }
+
+        let is_stream = |row: isize, col: isize| -> bool {
+            let z = streams.get_value(row, col);
+            !is_nodata(z, streams_nodata) && z > 0f64
+        };
+
+        // Measure the channel length within each stream cell and find the channel heads. A stream cell
+        // contains the channel that links it to its downstream neighbour; outlet cells contain one cell length.
+        let mut channel_length: Array2D<f64> = Array2D::new(rows, columns, 0f64, -1f64)?;
+        let mut channel_head: Array2D<f64> = Array2D::new(rows, columns, 0f64, -1f64)?;
+        let mut valid: Array2D<f64> = Array2D::new(rows, columns, 0f64, -1f64)?;
+        let mut dir: usize;
+        let mut c: usize;
+        let mut has_inflow: bool;
+        for row in 0..rows {
+            for col in 0..columns {
+                if is_nodata(pntr.get_value(row, col), pntr_nodata) {
+                    continue;
+                }
+                valid.set_value(row, col, 1f64);
+                if !is_stream(row, col) {
+                    continue;
+                }
+                dir = pntr.get_value(row, col) as usize;
+                if dir > 128 || (dir > 0 && pntr_matches[dir] == 999) {
+                    return Err(Error::new(ErrorKind::InvalidInput,
+                        "An unexpected value has been identified in the pointer image. This tool requires a pointer grid that has been created using either the D8 or Rho8 tools."));
+                }
+                if dir > 0 && is_stream(row + dy[pntr_matches[dir]], col + dx[pntr_matches[dir]]) {
+                    c = pntr_matches[dir];
+                    channel_length.set_value(row, col, grid_lengths[c]);
+                } else {
+                    channel_length.set_value(row, col, (cell_size_x + cell_size_y) / 2f64);
+                }
+                has_inflow = false;
+                for n in 0..8 {
+                    if is_stream(row + dy[n], col + dx[n])
+                        && pntr.get_value(row + dy[n], col + dx[n]) == inflowing_vals[n]
+                    {
+                        has_inflow = true;
+                        break;
+                    }
+                }
+                if !has_inflow {
+                    channel_head.set_value(row, col, 1f64);
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Measuring channels", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let out_nodata = -32768f64;
+        let mut output = Raster::initialize_using_file(&output_file, &pntr);
+        output.configs.data_type = DataType::F32;
+        output.configs.nodata = out_nodata;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "spectrum.plt".to_string();
+        output.reinitialize_values(out_nodata);
+        let mut sources_output = if !sources_file.is_empty() {
+            let mut r = Raster::initialize_using_file(&sources_file, &pntr);
+            r.configs.data_type = DataType::F32;
+            r.configs.nodata = out_nodata;
+            r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            r.configs.palette = "spectrum.plt".to_string();
+            r.reinitialize_values(out_nodata);
+            Some(r)
+        } else {
+            None
+        };
+
+        if watersheds_file.is_empty() {
+            // Moving window densities, calculated using summed-area tables.
+            let length_table = summed_area_table(&channel_length);
+            let heads_table = summed_area_table(&channel_head);
+            let valid_table = summed_area_table(&valid);
+            let midpoint = filter_size / 2;
+            let (mut y1, mut y2, mut x1, mut x2): (isize, isize, isize, isize);
+            let mut area: f64;
+            for row in 0..rows {
+                y1 = (row - midpoint).max(0);
+                y2 = (row + midpoint).min(rows - 1);
+                for col in 0..columns {
+                    if valid.get_value(row, col) == 0f64 {
+                        continue;
+                    }
+                    x1 = (col - midpoint).max(0);
+                    x2 = (col + midpoint).min(columns - 1);
+                    area = window_sum(&valid_table, y1, x1, y2, x2) * cell_area;
+                    output.set_value(row, col, window_sum(&length_table, y1, x1, y2, x2) / area);
+                    if let Some(ref mut r) = sources_output {
+                        r.set_value(row, col, window_sum(&heads_table, y1, x1, y2, x2) / area);
+                    }
+                }
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Calculating densities", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+        } else {
+            // Per-watershed densities.
+            let watersheds = Shapefile::read(&watersheds_file)?;
+            if watersheds.header.shape_type.base_shape_type() != ShapeType::Polygon {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The input watersheds vector data must be of POLYGON base shape type.",
+                ));
+            }
+            let mut basin_stats = Vec::with_capacity(watersheds.num_records);
+            let (mut length, mut heads, mut area, mut drainage_density, mut source_density): (f64, f64, f64, f64, f64);
+            for record_num in 0..watersheds.num_records {
+                let record = watersheds.get_record(record_num);
+                let cells = polygon_cells(&pntr, &record);
+                length = 0f64;
+                heads = 0f64;
+                area = 0f64;
+                for &(row, col) in &cells {
+                    if valid.get_value(row, col) == 1f64 {
+                        length += channel_length.get_value(row, col);
+                        heads += channel_head.get_value(row, col);
+                        area += cell_area;
+                    }
+                }
+                drainage_density = if area > 0f64 { length / area } else { 0f64 };
+                source_density = if area > 0f64 { heads / area } else { 0f64 };
+                for &(row, col) in &cells {
+                    if valid.get_value(row, col) == 1f64 {
+                        output.set_value(row, col, drainage_density);
+                        if let Some(ref mut r) = sources_output {
+                            r.set_value(row, col, source_density);
+                        }
+                    }
+                }
+                basin_stats.push((length, heads, area, drainage_density, source_density));
+                reporter.check_cancelled()?;
+                if verbose {
+                    progress = (100.0_f64 * (record_num + 1) as f64 / watersheds.num_records as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Calculating densities", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+
+            if !basins_file.is_empty() {
+                let mut basins = Shapefile::initialize_using_file(&basins_file, &watersheds, watersheds.header.shape_type, true)?;
+                basins.attributes.add_field(&AttributeField::new("STRM_LEN", FieldDataType::Real, 12u8, 4u8));
+                basins.attributes.add_field(&AttributeField::new("NUM_SRCS", FieldDataType::Int, 8u8, 0u8));
+                basins.attributes.add_field(&AttributeField::new("AREA", FieldDataType::Real, 16u8, 4u8));
+                basins.attributes.add_field(&AttributeField::new("DRAIN_DEN", FieldDataType::Real, 14u8, 8u8));
+                basins.attributes.add_field(&AttributeField::new("SRC_DEN", FieldDataType::Real, 16u8, 12u8));
+                for record_num in 0..watersheds.num_records {
+                    reporter.check_cancelled()?;
+                    let record = watersheds.get_record(record_num);
+                    basins.add_record(record.clone());
+                    let (length, heads, area, drainage_density, source_density) = basin_stats[record_num];
+                    let mut atts = watersheds.attributes.get_record(record_num);
+                    atts.push(FieldData::Real(length));
+                    atts.push(FieldData::Int(heads as i32));
+                    atts.push(FieldData::Real(area));
+                    atts.push(FieldData::Real(drainage_density));
+                    atts.push(FieldData::Real(source_density));
+                    basins.attributes.add_record(atts, false);
+                }
+                if verbose {
+                    println!("Saving basins...")
+                };
+                let _ = match basins.write() {
+                    Ok(_) => {
+                        if verbose {
+                            println!("Output basins file written")
+                        }
+                    }
+                    Err(e) => return Err(e),
+                };
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let mut outputs = vec![(output, "Drainage density")];
+        if let Some(r) = sources_output {
+            outputs.push((r, "Source density"));
+        }
+        for (mut output, label) in outputs {
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("{}", label));
+            output.add_metadata_entry(format!("Input d8 pointer file: {}", d8_file));
+            output.add_metadata_entry(format!("Input streams file: {}", streams_file));
+            if !watersheds_file.is_empty() {
+                output.add_metadata_entry(format!("Input watersheds file: {}", watersheds_file));
+            } else {
+                output.add_metadata_entry(format!("Filter size: {}", filter_size));
+            }
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns a summed-area table of `data`, with an extra leading row and column of zeros.
+fn summed_area_table(data: &Array2D<f64>) -> Array2D<f64> {
+    let rows = data.rows();
+    let columns = data.columns();
+    let mut table: Array2D<f64> = Array2D::new(rows + 1, columns + 1, 0f64, -1f64)
+        .expect("Error creating summed-area table.");
+    let mut row_sum: f64;
+    for row in 0..rows {
+        row_sum = 0f64;
+        for col in 0..columns {
+            row_sum += data.get_value(row, col);
+            table.set_value(row + 1, col + 1, row_sum + table.get_value(row, col + 1));
+        }
+    }
+    table
+}
+
+/// Returns the sum of the cells from (`y1`, `x1`) to (`y2`, `x2`), inclusive.
+fn window_sum(table: &Array2D<f64>, y1: isize, x1: isize, y2: isize, x2: isize) -> f64 {
+    table.get_value(y2 + 1, x2 + 1) - table.get_value(y1, x2 + 1) - table.get_value(y2 + 1, x1)
+        + table.get_value(y1, x1)
+}
+
+/// Returns the grid cells whose centres lie within a polygon, accounting for holes.
+fn polygon_cells(grid: &Raster, record: &ShapefileGeometry) -> Vec<(isize, isize)> {
+    let rows = grid.configs.rows as isize;
+    let columns = grid.configs.columns as isize;
+    let top = grid.get_row_from_y(record.y_max).max(0);
+    let bottom = grid.get_row_from_y(record.y_min).min(rows - 1);
+    let left = grid.get_column_from_x(record.x_min).max(0);
+    let right = grid.get_column_from_x(record.x_max).min(columns - 1);
+    let mut parts = vec![];
+    for part in 0..record.num_parts as usize {
+        let part_start = record.parts[part] as usize;
+        let part_end = if part < record.num_parts as usize - 1 {
+            record.parts[part + 1] as usize
+        } else {
+            record.num_points as usize
+        };
+        parts.push((part_start, part_end));
+    }
+    let mut cells = vec![];
+    for row in top..=bottom {
+        let y = grid.get_y_from_row(row);
+        for col in left..=right {
+            let p = Point2D::new(grid.get_x_from_column(col), y);
+            let mut count = 0;
+            for &(start, end) in &parts {
+                if point_in_poly(&p, &record.points[start..end]) {
+                    count += 1;
+                }
+            }
+            if count % 2 == 1 {
+                cells.push((row, col));
+            }
+        }
+    }
+    cells
+}
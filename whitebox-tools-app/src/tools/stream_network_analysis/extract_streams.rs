@@ -10,14 +10,13 @@ NOTES: This tool should be updated to incorporate the option for an area-slope b
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to extract, or map, the likely stream cells from an input flow-accumulation image
 /// (`--flow_accum`). The algorithm applies a threshold to the input flow accumulation image such that streams
@@ -157,57 +156,16 @@ impl WhiteboxTool for ExtractStreams {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut flow_accum_file = String::new();
-        let mut output_file = String::new();
-        let mut fa_threshold = 0.0;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut flow_accum_file = parsed.get_string("flow_accum").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let fa_threshold = parsed.get_f64("threshold").unwrap_or(0.0);
         let mut background_val = f64::NEG_INFINITY;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-flow_accum" {
-                if keyval {
-                    flow_accum_file = vec[1].to_string();
-                } else {
-                    flow_accum_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-threshold" {
-                if keyval {
-                    fa_threshold = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    fa_threshold = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-zero_background" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    background_val = 0f64;
-                }
-            }
+        if parsed.get_bool("zero_background") {
+            background_val = 0f64;
         }
 
         if verbose {
@@ -221,17 +179,11 @@ impl WhiteboxTool for ExtractStreams {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !flow_accum_file.contains(&sep) && !flow_accum_file.contains("/") {
-            flow_accum_file = format!("{}{}", working_directory, flow_accum_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        flow_accum_file = resolve_input_path(&flow_accum_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -250,31 +202,29 @@ impl WhiteboxTool for ExtractStreams {
 
         let mut output = Raster::initialize_using_file(&output_file, &flow_accum);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let flow_accum = flow_accum.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = flow_accum[(row, col)];
-                        if z != nodata && z > fa_threshold {
+                        if !is_nodata(z, nodata) && z > fa_threshold {
                             data[col as usize] = 1.0;
-                        } else if z != nodata {
+                        } else if !is_nodata(z, nodata) {
                             data[col as usize] = background_val;
                         } else {
                             data[col as usize] = nodata;
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -282,10 +232,11 @@ impl WhiteboxTool for ExtractStreams {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
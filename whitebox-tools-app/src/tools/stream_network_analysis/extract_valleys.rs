@@ -8,15 +8,14 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::cmp::Ordering::Equal;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to extract channel networks from an input digital elevation models (`--dem`) using
 /// one of three techniques that are based on local topography alone.
@@ -120,7 +119,7 @@ impl ExtractValleys {
             flags: vec!["--line_thin".to_owned()], 
             description: "Optional flag indicating whether post-processing line-thinning should be performed.".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true
         });
 
@@ -199,79 +198,33 @@ impl WhiteboxTool for ExtractValleys {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut variant = String::from("lq");
-        let mut line_thin = false;
+        let line_thin = parsed.get_bool("line_thin");
         let mut filter_size = 5;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_string("variant") {
+            variant = value.to_string();
+            if variant.to_lowercase().contains("q") {
+                variant = String::from("lq");
+            } else if variant.to_lowercase().contains("j") {
+                variant = String::from("JandR");
+            } else {
+                //if variant.to_lowercase().contains("p") {
+                variant = String::from("PandD");
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-variant" {
-                if keyval {
-                    variant = vec[1].to_string();
-                } else {
-                    variant = args[i + 1].to_string();
-                }
-                if variant.to_lowercase().contains("q") {
-                    variant = String::from("lq");
-                } else if variant.to_lowercase().contains("j") {
-                    variant = String::from("JandR");
-                } else {
-                    //if variant.to_lowercase().contains("p") {
-                    variant = String::from("PandD");
-                }
-            } else if flag_val == "-line_thin" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    line_thin = true;
-                }
-            } else if flag_val == "-filter" {
-                if keyval {
-                    filter_size = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
+        }
+        if let Some(value) = parsed.get_f64("filter") {
+            filter_size = value as usize;
 
-                //the filter dimensions must be odd numbers such that there is a middle pixel
-                if (filter_size as f64 / 2f64).floor() == filter_size as f64 / 2f64 {
-                    println!("WARNING: Filter dimensions must be odd numbers. The specified filter dimension has been modified.");
-                    filter_size += 1;
-                }
+            //the filter dimensions must be odd numbers such that there is a middle pixel
+            if (filter_size as f64 / 2f64).floor() == filter_size as f64 / 2f64 {
+                println!("WARNING: Filter dimensions must be odd numbers. The specified filter dimension has been modified.");
+                filter_size += 1;
             }
         }
 
@@ -286,14 +239,8 @@ impl WhiteboxTool for ExtractValleys {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Arc::new(Raster::new(&input_file, "r")?);
 
@@ -312,17 +259,13 @@ impl WhiteboxTool for ExtractValleys {
                 output.reinitialize_values(0f64);
 
                 // This one can be performed conccurently.
-                let mut num_procs = num_cpus::get() as isize;
                 let configs = whitebox_common::configs::get_configs()?;
-                let max_procs = configs.max_procs;
-                if max_procs > 0 && max_procs < num_procs {
-                    num_procs = max_procs;
-                }
+                let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
                 let (tx, rx) = mpsc::channel();
                 for tid in 0..num_procs {
                     let input = input.clone();
                     let tx = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         let num_cells_in_filter = filter_size * filter_size;
                         let mut dx = vec![0isize; num_cells_in_filter];
                         let mut dy = vec![0isize; num_cells_in_filter];
@@ -354,13 +297,13 @@ impl WhiteboxTool for ExtractValleys {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 z = input.get_value(row, col);
-                                if z != nodata {
+                                if !is_nodata(z, nodata) {
                                     n = 0f64;
                                     // let mut cell_data = Vec::with_capacity(num_cells_in_filter);
                                     for i in 0..num_cells_in_filter {
                                         if filter_shape[i] {
                                             zn = input.get_value(row + dy[i], col + dx[i]);
-                                            if zn != nodata {
+                                            if !is_nodata(zn, nodata) {
                                                 // cell_data.push(zn);
                                                 cell_data[i] = zn;
                                                 n += 1f64;
@@ -382,7 +325,9 @@ impl WhiteboxTool for ExtractValleys {
                                     }
                                 }
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -390,10 +335,11 @@ impl WhiteboxTool for ExtractValleys {
                 for row in 0..rows {
                     let data = rx.recv().expect("Error receiving data from thread.");
                     output.set_row_data(data.0, data.1);
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress as usize);
                             old_progress = progress;
                         }
                     }
@@ -402,17 +348,13 @@ impl WhiteboxTool for ExtractValleys {
             "JandR" => {
                 // This one can be performed conccurently.
                 // output.reinitialize_values(0f64);
-                let mut num_procs = num_cpus::get() as isize;
                 let configs = whitebox_common::configs::get_configs()?;
-                let max_procs = configs.max_procs;
-                if max_procs > 0 && max_procs < num_procs {
-                    num_procs = max_procs;
-                }
+                let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
                 let (tx, rx) = mpsc::channel();
                 for tid in 0..num_procs {
                     let input = input.clone();
                     let tx = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         let (mut z, mut zn1, mut zn2): (f64, f64, f64);
                         let dx = [0, 0, -1, 1];
                         let dy = [-1, 1, 0, 0];
@@ -420,15 +362,15 @@ impl WhiteboxTool for ExtractValleys {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 z = input[(row, col)];
-                                if z != nodata {
+                                if !is_nodata(z, nodata) {
                                     zn1 = input[(row + dy[0], col + dx[0])];
                                     zn2 = input[(row + dy[1], col + dx[1])];
-                                    if zn1 != nodata && zn2 != nodata && zn1 > z && zn2 > z {
+                                    if !is_nodata(zn1, nodata) && !is_nodata(zn2, nodata) && zn1 > z && zn2 > z {
                                         data[col as usize] = 1f64;
                                     } else {
                                         zn1 = input[(row + dy[2], col + dx[2])];
                                         zn2 = input[(row + dy[3], col + dx[3])];
-                                        if zn1 != nodata && zn2 != nodata && zn1 > z && zn2 > z {
+                                        if !is_nodata(zn1, nodata) && !is_nodata(zn2, nodata) && zn1 > z && zn2 > z {
                                             data[col as usize] = 1f64;
                                         } else {
                                             data[col as usize] = 0f64;
@@ -436,7 +378,9 @@ impl WhiteboxTool for ExtractValleys {
                                     }
                                 }
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -444,10 +388,11 @@ impl WhiteboxTool for ExtractValleys {
                 for row in 0..rows {
                     let data = rx.recv().expect("Error receiving data from thread.");
                     output.set_row_data(data.0, data.1);
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress as usize);
                             old_progress = progress;
                         }
                     }
@@ -467,12 +412,12 @@ impl WhiteboxTool for ExtractValleys {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             maxz = z;
                             which_cell = 3;
                             for n in 0..num_scan_cells {
                                 z = input[(row + dy[n], col + dx[n])];
-                                if z != nodata {
+                                if !is_nodata(z, nodata) {
                                     if z > maxz {
                                         maxz = z;
                                         which_cell = n;
@@ -484,10 +429,11 @@ impl WhiteboxTool for ExtractValleys {
                             output[(row, col)] = nodata;
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                         if progress != old_progress {
-                            println!("Progress: {}%", progress);
+                            reporter.progress("Progress", progress as usize);
                             old_progress = progress;
                         }
                     }
@@ -533,7 +479,7 @@ impl WhiteboxTool for ExtractValleys {
                     for row in 0..rows {
                         for col in 0..columns {
                             z = output[(row, col)];
-                            if z > 0.0 && z != nodata {
+                            if z > 0.0 && !is_nodata(z, nodata) {
                                 // fill the neighbours array
                                 for i in 0..8 {
                                     neighbours[i] = output[(row + dy[i], col + dx[i])];
@@ -553,10 +499,11 @@ impl WhiteboxTool for ExtractValleys {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * a as f64 / 7.0) as i32;
                         if progress != old_progress {
-                            println!("Loop Number {}: {}%", loop_num, progress);
+                            reporter.progress(&format!("Loop Number {}", loop_num), progress as usize);
                             old_progress = progress;
                         }
                     }
@@ -1,5 +1,7 @@
 // private sub-module defined in other files
+mod conform_streams_to_hydrography;
 mod dist_to_outlet;
+mod drainage_density;
 mod extract_streams;
 mod extract_valleys;
 mod farthest_channel_head;
@@ -18,12 +20,15 @@ mod stream_link_id;
 mod stream_link_length;
 mod stream_link_slope;
 mod stream_slope_continuous;
+mod stream_solar_exposure;
 mod topological_stream_order;
 mod total_length_channels;
 mod tributary_id;
 
 // exports identifiers from private sub-modules in the current module namespace
+pub use self::conform_streams_to_hydrography::ConformStreamsToHydrography;
 pub use self::dist_to_outlet::DistanceToOutlet;
+pub use self::drainage_density::DrainageDensity;
 pub use self::extract_streams::ExtractStreams;
 pub use self::extract_valleys::ExtractValleys;
 pub use self::farthest_channel_head::FarthestChannelHead;
@@ -42,6 +47,7 @@ pub use self::stream_link_id::StreamLinkIdentifier;
 pub use self::stream_link_length::StreamLinkLength;
 pub use self::stream_link_slope::StreamLinkSlope;
 pub use self::stream_slope_continuous::StreamSlopeContinuous;
+pub use self::stream_solar_exposure::StreamSolarExposure;
 pub use self::topological_stream_order::TopologicalStreamOrder;
 pub use self::total_length_channels::LengthOfUpstreamChannels;
 pub use self::tributary_id::TributaryIdentifier;
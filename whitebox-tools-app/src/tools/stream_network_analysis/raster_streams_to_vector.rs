@@ -75,7 +75,7 @@ impl RasterStreamsToVector {
 
         parameters.push(ToolParameter {
             name: "Does the pointer file use the ESRI pointer scheme?".to_owned(),
-            flags: vec!["--esri_pntr".to_owned()],
+            flags: vec!["--esri_style".to_owned(), "--esri_pntr".to_owned()],
             description: "D8 pointer uses the ESRI style scheme.".to_owned(),
             parameter_type: ParameterType::Boolean,
             default_value: Some("false".to_owned()),
@@ -148,52 +148,13 @@ impl WhiteboxTool for RasterStreamsToVector {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut d8_file = String::new();
-        let mut streams_file = String::new();
-        let mut output_file = String::new();
-        let mut esri_style = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-d8_pntr" {
-                d8_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-streams" {
-                streams_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-esri_pntr" || flag_val == "-esri_style" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    esri_style = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut d8_file = parsed.get_string("d8_pntr").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let esri_style = parsed.get_bool("esri_pntr");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -206,20 +167,12 @@ impl WhiteboxTool for RasterStreamsToVector {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !d8_file.contains(&sep) && !d8_file.contains("/") {
-            d8_file = format!("{}{}", working_directory, d8_file);
-        }
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        d8_file = resolve_input_path(&d8_file, working_directory)?;
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading pointer data...")
@@ -242,11 +195,9 @@ impl WhiteboxTool for RasterStreamsToVector {
         if streams.configs.rows != pntr.configs.rows
             || streams.configs.columns != pntr.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&streams, &pntr, verbose);
 
         // create output file
         let mut output = Shapefile::new(&output_file, ShapeType::PolyLine)?;
@@ -281,11 +232,11 @@ impl WhiteboxTool for RasterStreamsToVector {
         let mut count: i8;
         for row in 0..rows {
             for col in 0..columns {
-                if streams.get_value(row, col) > 0.0 && streams.get_value(row, col) != nodata {
+                if streams.get_value(row, col) > 0.0 && !is_nodata(streams.get_value(row, col), nodata) {
                     count = 0i8;
                     for i in 0..8 {
                         if streams.get_value(row + dy[i], col + dx[i]) > 0.0
-                            && streams.get_value(row + dy[i], col + dx[i]) != nodata
+                            && !is_nodata(streams.get_value(row + dy[i], col + dx[i]), nodata)
                             && pntr.get_value(row + dy[i], col + dx[i]) == inflowing_vals[i]
                         {
                             count += 1;
@@ -300,10 +251,11 @@ impl WhiteboxTool for RasterStreamsToVector {
                     num_solved_cells += 1;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -363,7 +315,7 @@ impl WhiteboxTool for RasterStreamsToVector {
                 prev_dir = 99; // this way the first point in the line is always output.
                 flag = true;
                 while flag {
-                    if pntr.get_value(row, col) != pntr_nodata {
+                    if !is_nodata(pntr.get_value(row, col), pntr_nodata) {
                         dir = pntr.get_value(row, col) as usize;
                         already_added_point = if dir != prev_dir {
                             x = pntr.get_x_from_column(col);
@@ -376,7 +328,7 @@ impl WhiteboxTool for RasterStreamsToVector {
                         };
                         if dir > 0
                             && streams.get_value(row, col) > 0.0
-                            && streams.get_value(row, col) != nodata
+                            && !is_nodata(streams.get_value(row, col), nodata)
                         {
                             if dir > 128 || pntr_matches[dir] == 999 {
                                 return Err(Error::new(
@@ -432,10 +384,11 @@ impl WhiteboxTool for RasterStreamsToVector {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * num_solved_cells as f64 / (num_cells - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
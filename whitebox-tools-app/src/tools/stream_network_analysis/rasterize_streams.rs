@@ -75,7 +75,7 @@ impl RasterizeStreams {
             flags: vec!["--nodata".to_owned()],
             description: "Use NoData value for background?".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true,
         });
 
@@ -153,58 +153,15 @@ impl WhiteboxTool for RasterizeStreams {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut streams_file = String::new();
-        let mut base_file = String::new();
-        let mut output_file = String::new();
-        let mut out_nodata = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut base_file = parsed.get_string("base").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let out_nodata = parsed.get_bool("nodata");
         let mut background_val = 0f64;
-        let mut feature_id = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-streams" {
-                streams_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-base" {
-                base_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-nodata" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    out_nodata = true;
-                }
-            } else if flag_val == "-feature_id" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    feature_id = true;
-                }
-            }
-        }
+        let feature_id = parsed.get_bool("feature_id");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -217,20 +174,12 @@ impl WhiteboxTool for RasterizeStreams {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !streams_file.contains(&sep) && !streams_file.contains("/") {
-            streams_file = format!("{}{}", working_directory, streams_file);
-        }
-        if !base_file.contains(&sep) && !base_file.contains("/") {
-            base_file = format!("{}{}", working_directory, base_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        base_file = resolve_input_path(&base_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading streams data...")
@@ -392,10 +341,11 @@ impl WhiteboxTool for RasterizeStreams {
             }
 
             count += 1f64;
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * count / (streams.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Rasterizing Streams: {}%", progress);
+                    reporter.progress("Rasterizing Streams", progress);
                     old_progress = progress;
                 }
             }
@@ -432,10 +382,11 @@ impl WhiteboxTool for RasterizeStreams {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -451,10 +402,11 @@ impl WhiteboxTool for RasterizeStreams {
                         output.set_value(row, col, 1f64);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
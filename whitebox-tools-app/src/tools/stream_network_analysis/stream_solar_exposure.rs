@@ -0,0 +1,569 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use crate::tools::terrain_analysis::solar_position;
+use whitebox_raster::*;
+use crate::tools::*;
+use whitebox_vector::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool estimates the exposure of stream reaches to direct solar radiation, accounting for shading by both
+/// the surrounding topography and riparian vegetation. Stream temperature is strongly controlled by solar loading,
+/// and the loss of riparian shade is a common cause of thermal impairment of fish habitat. The tool is intended
+/// for assessing the thermal loading of stream reaches, e.g. to prioritize riparian planting.
+///
+/// The user must specify the input stream network vector (`--streams`), in which each polyline record is treated
+/// as a reach, a bare-earth digital elevation model (`--dem`), and a canopy height model (`--canopy`), i.e. a raster
+/// of vegetation heights above the ground surface. Canopy nodata values are treated as zero height. Each reach is
+/// sampled at a regular spacing (`--spacing`; by default the DEM grid resolution). The sun's position is calculated
+/// for every time step (`--time_step`, in minutes) of every day from the starting day (`--start_day`) to the ending
+/// day (`--end_day`) of the year, for the location specified by `--lat` and `--long`; if these are unspecified and
+/// the DEM is in geographic coordinates, the centre of the DEM is used. For each sample, the horizon angle of the
+/// combined ground and canopy surface is calculated within each of a number of azimuth sectors (`--az_fraction`,
+/// in degrees) out to a maximum search distance (`--max_dist`), and the sample is deemed shaded whenever the sun is
+/// below the horizon in its azimuth sector. Samples lying beneath canopy, i.e. where the canopy height at the stream
+/// location is greater than zero, are treated as shaded by overhanging vegetation.
+///
+/// The clear-sky direct-beam irradiance on a horizontal water surface is estimated using a simple atmospheric
+/// transmissivity (`--transmissivity`) model, *I = S<sub>0</sub> τ<sup>m</sup> sin(β)*, where *S<sub>0</sub>* is the
+/// solar constant (1367 W/m<sup>2</sup>), *τ* is the transmissivity, *m = 1 / sin(β)* is the relative air mass, and
+/// *β* is the solar altitude. Diffuse radiation is not modelled. The output vector (`--output`) is a copy of the input
+/// streams with the following attributes added to each reach:
+///
+/// | Field | Description |
+/// |-------|-------------|
+/// | LENGTH | Reach length, in map units |
+/// | SHADE_FRAC | Mean fraction of daylight time for which the reach is shaded |
+/// | SHADE_LEN | Length of the reach that is shaded for more than half of the daylight time |
+/// | POT_KWH | Potential (unshaded) direct solar energy over the period, in kWh/m<sup>2</sup> |
+/// | SOLAR_KWH | Incident (shaded) direct solar energy over the period, in kWh/m<sup>2</sup> |
+///
+/// The input rasters should be in a projected coordinate system with vertical units that match the horizontal units.
+/// Run times increase with the number of samples, the number of azimuth sectors, and the search distance.
+///
+/// # See Also
+/// `TimeInDaylight`, `HorizonAngle`, `DrainageDensity`
+pub struct StreamSolarExposure {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl StreamSolarExposure {
+    pub fn new() -> StreamSolarExposure {
+        // public constructor
+        let name = "StreamSolarExposure".to_string();
+        let toolbox = "Stream Network Analysis".to_string();
+        let description = "Calculates the shaded fraction and incident direct solar energy of stream reaches.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Streams File".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input vector streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--dem".to_owned()],
+            description: "Input raster bare-earth DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Canopy Height File".to_owned(),
+            flags: vec!["--canopy".to_owned()],
+            description: "Input raster canopy height model file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector streams file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Latitude (optional)".to_owned(),
+            flags: vec!["--latitude".to_owned(), "--lat".to_owned()],
+            description: "Centre point latitude.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Longitude (optional)".to_owned(),
+            flags: vec!["--longitude".to_owned(), "--long".to_owned()],
+            description: "Centre point longitude.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Start Day Of The Year (1-365)".to_owned(),
+            flags: vec!["--start_day".to_owned()],
+            description: "Start day of the year (1-365).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("152".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "End Day Of The Year (1-365)".to_owned(),
+            flags: vec!["--end_day".to_owned()],
+            description: "End day of the year (1-365).".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("243".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Time Step (minutes)".to_owned(),
+            flags: vec!["--time_step".to_owned()],
+            description: "Time step of the solar almanac, in minutes.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("30".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Azimuth Fraction (degrees)".to_owned(),
+            flags: vec!["--az_fraction".to_owned()],
+            description: "Azimuth sector size, in degrees.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("5.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Search Distance".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Maximum horizon search distance, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("100.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Sample Spacing (optional)".to_owned(),
+            flags: vec!["--spacing".to_owned()],
+            description: "Spacing of reach samples, in map units; defaults to the grid resolution.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Atmospheric Transmissivity".to_owned(),
+            flags: vec!["--transmissivity".to_owned()],
+            description: "Clear-sky atmospheric transmissivity (0-1).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.7".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" --streams=streams.shp --dem=DEM.tif --canopy=chm.tif -o=stream_exposure.shp --lat=43.545 --long=-80.248 --start_day=152 --end_day=243 --time_step=30 --max_dist=100.0", short_exe, name).replace("*", &sep);
+
+        StreamSolarExposure {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for StreamSolarExposure {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut canopy_file = parsed.get_string("canopy").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut latitude = parsed.get_f64("lat").unwrap_or(f64::NAN);
+        let mut longitude = parsed.get_f64("long").unwrap_or(f64::NAN);
+        let start_day = parsed.get_usize("start_day")?.unwrap_or(152usize);
+        let end_day = parsed.get_usize("end_day")?.unwrap_or(243usize);
+        let mut time_step = parsed.get_usize("time_step")?.unwrap_or(30usize);
+        let mut az_fraction = parsed.get_f64("az_fraction").unwrap_or(5f64);
+        let max_dist = parsed.get_f64("max_dist").unwrap_or(100f64);
+        let mut spacing = parsed.get_f64("spacing").unwrap_or(0f64);
+        let transmissivity = parsed.get_f64("transmissivity").unwrap_or(0.7f64);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        for file in [&mut streams_file, &mut dem_file, &mut canopy_file, &mut output_file] {
+            *file = resolve_path(file, working_directory);
+        }
+        if start_day < 1 || start_day > 365 || end_day < 1 || end_day > 365 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The start and end days must be between 1 and 365.",
+            ));
+        }
+        if time_step < 1 {
+            time_step = 1;
+        }
+        if az_fraction <= 0f64 || az_fraction > 90f64 {
+            az_fraction = 5f64;
+        }
+        if transmissivity <= 0f64 || transmissivity > 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The atmospheric transmissivity must be between 0 and 1.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let streams = Shapefile::read(&streams_file)?;
+        let dem = Arc::new(Raster::new(&dem_file, "r")?);
+        let canopy = Arc::new(Raster::new(&canopy_file, "r")?);
+
+        let start = Instant::now();
+
+        if streams.header.shape_type.base_shape_type() != ShapeType::PolyLine {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The input streams vector data must be of POLYLINE base shape type.",
+            ));
+        }
+
+        if latitude.is_nan() || longitude.is_nan() {
+            if !dem.is_in_geographic_coordinates() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The latitude (--lat) and longitude (--long) must be specified for DEMs in projected coordinates.",
+                ));
+            }
+            latitude = (dem.configs.north + dem.configs.south) / 2f64;
+            longitude = (dem.configs.east + dem.configs.west) / 2f64;
+        }
+        if latitude > 90.0 || latitude < -90.0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The specified latitude must be between -90 and 90 degrees.",
+            ));
+        }
+        if longitude < -180.0 || longitude > 180.0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The specified longitude must be between -180 and 180 degrees.",
+            ));
+        }
+
+        // Solar almanac: sector, altitude, and direct-beam energy (kWh/m^2) of each daylight time step.
+        const SOLAR_CONSTANT: f64 = 1367f64; // W/m^2
+        const JAN_1_2021: i64 = 1_609_459_200; // unix time, in seconds
+        let num_sectors = (360f64 / az_fraction).ceil() as usize;
+        let days: Vec<usize> = if start_day <= end_day {
+            (start_day..=end_day).collect()
+        } else {
+            (start_day..=365).chain(1..=end_day).collect()
+        };
+        let mut almanac: Vec<(usize, f64, f64)> = vec![];
+        for &doy in &days {
+            for minute in (0..1440).step_by(time_step) {
+                let unixtime = (JAN_1_2021 + (doy as i64 - 1) * 86400 + minute as i64 * 60) * 1000;
+                let pos = solar_position(unixtime, latitude, longitude);
+                if pos.altitude > 0f64 {
+                    let sector = ((pos.azimuth.to_degrees().rem_euclid(360f64) / az_fraction) as usize)
+                        .min(num_sectors - 1);
+                    let irradiance = SOLAR_CONSTANT
+                        * transmissivity.powf(1f64 / pos.altitude.sin())
+                        * pos.altitude.sin();
+                    almanac.push((sector, pos.altitude, irradiance * time_step as f64 / 60f64 / 1000f64));
+                }
+            }
+        }
+        if almanac.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The sun does not rise above the horizon during the specified period.",
+            ));
+        }
+        let potential_energy = almanac.iter().map(|a| a.2).sum::<f64>();
+
+        if spacing <= 0f64 {
+            spacing = (dem.configs.resolution_x + dem.configs.resolution_y) / 2f64;
+        }
+        let step_size = (dem.configs.resolution_x + dem.configs.resolution_y) / 2f64;
+
+        let streams = Arc::new(streams);
+        let almanac = Arc::new(almanac);
+        let num_records = streams.num_records;
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads();
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let streams = streams.clone();
+            let dem = dem.clone();
+            let canopy = canopy.clone();
+            let almanac = almanac.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let dem_nodata = dem.configs.nodata;
+                let canopy_nodata = canopy.configs.nodata;
+                let surface = |x: f64, y: f64| -> Option<(f64, f64)> {
+                    let z = dem.get_value(dem.get_row_from_y(y), dem.get_column_from_x(x));
+                    if is_nodata(z, dem_nodata) {
+                        return None;
+                    }
+                    let mut h = canopy.get_value(canopy.get_row_from_y(y), canopy.get_column_from_x(x));
+                    if is_nodata(h, canopy_nodata) || h < 0f64 {
+                        h = 0f64;
+                    }
+                    Some((z, h))
+                };
+                let sector_dirs: Vec<(f64, f64)> = (0..num_sectors)
+                    .map(|s| {
+                        let az = ((s as f64 + 0.5) * az_fraction).to_radians();
+                        (az.sin(), az.cos())
+                    })
+                    .collect();
+                for record_num in (0..num_records).filter(|r| r % num_procs == tid) {
+                    let record = streams.get_record(record_num);
+
+                    // sample the reach
+                    let mut length = 0f64;
+                    let mut samples = vec![];
+                    for part in 0..record.num_parts as usize {
+                        let part_start = record.parts[part] as usize;
+                        let part_end = if part < record.num_parts as usize - 1 {
+                            record.parts[part + 1] as usize - 1
+                        } else {
+                            record.num_points as usize - 1
+                        };
+                        let mut dist_to_next = spacing / 2f64;
+                        for i in part_start..part_end {
+                            let (x1, y1) = (record.points[i].x, record.points[i].y);
+                            let (x2, y2) = (record.points[i + 1].x, record.points[i + 1].y);
+                            let seg_length = ((x2 - x1) * (x2 - x1) + (y2 - y1) * (y2 - y1)).sqrt();
+                            length += seg_length;
+                            let mut d = dist_to_next;
+                            while d <= seg_length {
+                                samples.push((
+                                    x1 + (x2 - x1) * d / seg_length,
+                                    y1 + (y2 - y1) * d / seg_length,
+                                ));
+                                d += spacing;
+                            }
+                            dist_to_next = d - seg_length;
+                        }
+                    }
+
+                    let mut shaded_time = 0f64;
+                    let mut num_shaded_samples = 0usize;
+                    let mut incident_energy = 0f64;
+                    let mut num_samples = 0usize;
+                    let mut horizon = vec![0f64; num_sectors];
+                    for &(x, y) in &samples {
+                        let (z0, h0) = match surface(x, y) {
+                            Some(v) => v,
+                            None => continue,
+                        };
+                        num_samples += 1;
+                        if h0 > 0f64 {
+                            // beneath overhanging canopy
+                            shaded_time += 1f64;
+                            num_shaded_samples += 1;
+                            continue;
+                        }
+                        for s in 0..num_sectors {
+                            let (dx, dy) = sector_dirs[s];
+                            let mut max_slope = f64::NEG_INFINITY;
+                            let mut d = step_size;
+                            while d <= max_dist {
+                                if let Some((z, h)) = surface(x + dx * d, y + dy * d) {
+                                    max_slope = max_slope.max((z + h - z0) / d);
+                                }
+                                d += step_size;
+                            }
+                            horizon[s] = max_slope.atan();
+                        }
+                        let mut num_shaded = 0usize;
+                        for &(sector, altitude, energy) in almanac.iter() {
+                            if altitude <= horizon[sector] {
+                                num_shaded += 1;
+                            } else {
+                                incident_energy += energy;
+                            }
+                        }
+                        let fraction = num_shaded as f64 / almanac.len() as f64;
+                        shaded_time += fraction;
+                        if fraction > 0.5 {
+                            num_shaded_samples += 1;
+                        }
+                    }
+
+                    let result = if num_samples > 0 {
+                        (
+                            length,
+                            shaded_time / num_samples as f64,
+                            length * num_shaded_samples as f64 / num_samples as f64,
+                            incident_energy / num_samples as f64,
+                            true,
+                        )
+                    } else {
+                        (length, 0f64, 0f64, 0f64, false)
+                    };
+                    if tx.send((record_num, result)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut results = vec![(0f64, 0f64, 0f64, 0f64, false); num_records];
+        for i in 0..num_records {
+            let (record_num, result) = rx.recv().expect("Error receiving data from thread.");
+            results[record_num] = result;
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * (i + 1) as f64 / num_records as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &streams, streams.header.shape_type, true)?;
+        output.attributes.add_field(&AttributeField::new("LENGTH", FieldDataType::Real, 12u8, 3u8));
+        output.attributes.add_field(&AttributeField::new("SHADE_FRAC", FieldDataType::Real, 8u8, 5u8));
+        output.attributes.add_field(&AttributeField::new("SHADE_LEN", FieldDataType::Real, 12u8, 3u8));
+        output.attributes.add_field(&AttributeField::new("POT_KWH", FieldDataType::Real, 12u8, 3u8));
+        output.attributes.add_field(&AttributeField::new("SOLAR_KWH", FieldDataType::Real, 12u8, 3u8));
+        for record_num in 0..num_records {
+            reporter.check_cancelled()?;
+            let record = streams.get_record(record_num);
+            output.add_record(record.clone());
+            let (length, shade_frac, shade_len, energy, valid) = results[record_num];
+            let mut atts = streams.attributes.get_record(record_num);
+            atts.push(FieldData::Real(length));
+            if valid {
+                atts.push(FieldData::Real(shade_frac));
+                atts.push(FieldData::Real(shade_len));
+                atts.push(FieldData::Real(potential_energy));
+                atts.push(FieldData::Real(energy));
+            } else {
+                for _ in 0..4 {
+                    atts.push(FieldData::Null);
+                }
+            }
+            output.attributes.add_record(atts, false);
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
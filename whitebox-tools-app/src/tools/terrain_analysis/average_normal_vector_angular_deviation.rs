@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool characterizes the spatial distribution of the average normal vector angular deviation, a measure of
 /// surface roughness. Working in the field of 3D printing, Ko et al. (2016) defined a measure of surface roughness
@@ -57,7 +56,7 @@ impl AverageNormalVectorAngularDeviation {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -66,7 +65,7 @@ impl AverageNormalVectorAngularDeviation {
 
         parameters.push(ToolParameter {
             name: "Output Raster File".to_owned(),
-            flags: vec!["--output".to_owned()],
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
             description: "Output raster file.".to_owned(),
             parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
             default_value: None,
@@ -95,7 +94,7 @@ impl AverageNormalVectorAngularDeviation {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --out_mag=roughness_mag.tif --out_scale=roughness_scale.tif --min_scale=1 --max_scale=1000 --step=5", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=output.tif --filter=11", short_exe, name).replace("*", &sep);
 
         AverageNormalVectorAngularDeviation {
             name: name,
@@ -147,52 +146,12 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size = 11usize;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-filter" {
-                filter_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(11usize);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -205,8 +164,6 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         if filter_size < 3 {
             filter_size = 3;
         }
@@ -227,12 +184,8 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
             ));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -309,12 +262,8 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
             let d_y = Arc::new(d_y);
             let weights = Arc::new(weights);
 
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let input = input.clone();
@@ -322,7 +271,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                 let d_y = d_y.clone();
                 let weights = weights.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut sum, mut z_final): (f64, f64);
                     let mut z: f64;
                     let mut zn: f64;
@@ -331,14 +280,14 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 sum = 0.0;
                                 z_final = 0.0;
                                 for a in 0..num_pixels_in_filter {
                                     x = col + d_x[a];
                                     y = row + d_y[a];
                                     zn = input.get_value(y, x);
-                                    if zn != nodata {
+                                    if !is_nodata(zn, nodata) {
                                         sum += weights[a];
                                         z_final += weights[a] * zn;
                                     }
@@ -349,7 +298,9 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                             }
                         }
 
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -404,7 +355,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                         sum_n = 0;
                         for col in 0..columns {
                             val = input.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata(val, nodata) {
                                 val = 0f64;
                             } else {
                                 sum_n += 1;
@@ -428,7 +379,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                         sum = 0f64;
                         for col in 0..columns {
                             val = smoothed_dem.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata(val, nodata) {
                                 val = 0f64;
                             }
                             sum += val;
@@ -454,7 +405,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                     }
 
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata(input.get_value(row, col), nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -496,19 +447,15 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
             }
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
         let smoothed_dem = Arc::new(smoothed_dem);
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let smoothed_dem = smoothed_dem.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let mut n1: [f64; 8] = [0.0; 8];
@@ -521,16 +468,16 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                     let mut data = vec![0f64; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             for c in 0..8 {
                                 n1[c] = input.get_value(row + dy[c], col + dx[c]);
-                                if n1[c] != nodata {
+                                if !is_nodata(n1[c], nodata) {
                                     n1[c] = n1[c] * z_factor;
                                 } else {
                                     n1[c] = input.get_value(row, col) * z_factor;
                                 }
                                 n2[c] = smoothed_dem.get_value(row + dy[c], col + dx[c]);
-                                if n2[c] != nodata {
+                                if !is_nodata(n2[c], nodata) {
                                     n2[c] = n2[c] * z_factor;
                                 } else {
                                     n2[c] = z * z_factor;
@@ -554,7 +501,9 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                             .to_degrees();
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -566,10 +515,11 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
             let data = rx.recv().expect("Error receiving data from thread.");
             angular_diff.set_row_data(data.0, data.1);
             // output.set_row_data(data.0, angular_diff.get_row_data(data.0));
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating angular deviation data: {}%", progress);
+                    reporter.progress("Calculating angular deviation data", progress);
                     old_progress = progress;
                 }
             }
@@ -585,7 +535,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                 sumn = 0u32;
                 for col in 0..columns {
                     sum += angular_diff.get_value(row, col);
-                    if smoothed_dem.get_value(row, col) == nodata {
+                    if is_nodata(smoothed_dem.get_value(row, col), nodata) {
                         // it's either nodata or a flag cell in the DEM.
                         i_n.decrement(row, col, 1);
                     }
@@ -594,22 +544,23 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                     i_n.set_value(row, col, sumn + i_n.get_value(row - 1, col));
                 }
             } else {
-                if smoothed_dem.get_value(0, 0) == nodata {
+                if is_nodata(smoothed_dem.get_value(0, 0), nodata) {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
                     angular_diff.increment(row, col, angular_diff.get_value(row, col - 1));
                     i_n.increment(row, col, i_n.get_value(row, col - 1));
-                    if smoothed_dem.get_value(row, col) == nodata {
+                    if is_nodata(smoothed_dem.get_value(row, col), nodata) {
                         // it's either nodata or a flag cell in the DEM.
                         i_n.decrement(row, col, 1);
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
+                    reporter.progress("Creating integral images", progress);
                     old_progress = progress;
                 }
             }
@@ -623,7 +574,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
             let smoothed_dem = smoothed_dem.clone();
             let i_n = i_n.clone();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
                 let mut n: f64;
                 let mut sum: f64;
@@ -647,7 +598,7 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -677,14 +628,8 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                         }
                     }
 
-                    match tx2.send((row, data)) {
-                        Ok(_) => {}
-                        Err(_) => {
-                            println!(
-                                "Error sending data from thread {} processing row {}.",
-                                tid, row
-                            );
-                        }
+                    if tx2.send((row, data)).is_err() {
+                        return;
                     }
                 }
             });
@@ -710,10 +655,11 @@ impl WhiteboxTool for AverageNormalVectorAngularDeviation {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    reporter.progress("Performing analysis", progress);
                     old_progress = progress;
                 }
             }
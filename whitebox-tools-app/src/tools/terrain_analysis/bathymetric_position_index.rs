@@ -0,0 +1,438 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use whitebox_raster::*;
+use whitebox_common::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool calculates the bathymetric position index (BPI) from an input bathymetric digital
+/// elevation model (`--dem`). BPI is a measure of where a grid cell is relative to the surrounding
+/// seascape and is the marine analogue of the topographic position index. It is calculated as the
+/// difference between the depth of a cell and the mean depth within an annulus (ring) of cells
+/// surrounding it, defined by an inner radius (`--inner_radius`) and an outer radius (`--outer_radius`),
+/// both measured in grid cells. Cells lying at distances from the centre cell that are between the
+/// inner and outer radii, inclusively, are included in the annulus. Positive BPI values indicate
+/// features that are higher than their surroundings (e.g. ridges and banks), negative values indicate
+/// features that are lower than their surroundings (e.g. depressions and channels), and values near
+/// zero indicate flat areas or areas of constant slope.
+///
+/// BPI is scale dependent. Fine-scale BPI, calculated using small radii, identifies small features within
+/// the seafloor, while broad-scale BPI, calculated using large radii, identifies larger regions such as
+/// banks and basins. The *scale factor* of an index is the outer radius multiplied by the grid resolution.
+///
+/// BPI values are sensitive to the relief of the seafloor, which makes it difficult to compare indices
+/// calculated at different scales. When the `--standardize` flag is specified, the output is transformed
+/// into a standardized BPI, i.e. the BPI is converted to a z-score using the mean and standard
+/// deviation of the index over the raster, multiplied by 100 and rounded to the nearest integer.
+/// A standardized BPI of 100 is therefore one standard deviation above the mean. This is the form of
+/// the index that is used by `BenthicTerrainClassification`, following the Benthic Terrain Modeler
+/// of Lundblad et al. (2006).
+///
+/// # Reference
+/// Lundblad, E. R., Wright, D. J., Miller, J., Larkin, E. M., Rinehart, R., Naar, D. F., Donahue, B. T.,
+/// Anderson, S. M., and Battista, T. (2006). A benthic terrain classification scheme for American Samoa.
+/// *Marine Geodesy*, 29(2), 89-111.
+///
+/// # See Also
+/// `BenthicTerrainClassification`, `DevFromMeanElev`, `DiffFromMeanElev`, `RelativeTopographicPosition`
+pub struct BathymetricPositionIndex {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl BathymetricPositionIndex {
+    pub fn new() -> BathymetricPositionIndex {
+        // public constructor
+        let name = "BathymetricPositionIndex".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Calculates the bathymetric position index (BPI) of a bathymetric DEM using an annulus neighbourhood."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster bathymetric DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Inner Radius (cells)".to_owned(),
+            flags: vec!["--inner_radius".to_owned()],
+            description: "Inner radius of the annulus neighbourhood, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Outer Radius (cells)".to_owned(),
+            flags: vec!["--outer_radius".to_owned()],
+            description: "Outer radius of the annulus neighbourhood, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Standardize?".to_owned(),
+            flags: vec!["--standardize".to_owned()],
+            description: "Output the standardized BPI (z-score multiplied by 100).".to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=bathy.tif -o=broad_bpi.tif --inner_radius=5 --outer_radius=25 --standardize",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        BathymetricPositionIndex {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for BathymetricPositionIndex {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut inner_radius = parsed.get_i64("inner_radius").map(|v| v as isize).unwrap_or(1isize);
+        let outer_radius = parsed.get_i64("outer_radius").map(|v| v as isize).unwrap_or(3isize);
+        let standardize = parsed.get_bool("standardize");
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        if inner_radius < 0 {
+            inner_radius = 0;
+        }
+        if outer_radius < 1 || outer_radius <= inner_radius {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The outer radius must be at least one cell and larger than the inner radius.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let bpi = bathymetric_position_index(&input, inner_radius, outer_radius, standardize, verbose, reporter)?;
+
+        let mut configs = input.configs.clone();
+        if standardize {
+            configs.data_type = DataType::I32;
+            configs.nodata = -32768f64;
+        } else {
+            configs.data_type = DataType::F32;
+        }
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        let out_nodata = output.configs.nodata;
+        let nodata = bpi.nodata();
+        for row in 0..bpi.rows() {
+            reporter.check_cancelled()?;
+            let mut data = bpi.get_row_data(row);
+            for z in data.iter_mut() {
+                if is_nodata(*z, nodata) {
+                    *z = out_nodata;
+                }
+            }
+            output.set_row_data(row, data);
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "blue_white_red.plt".to_string();
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Inner radius: {}", inner_radius));
+        output.add_metadata_entry(format!("Outer radius: {}", outer_radius));
+        output.add_metadata_entry(format!(
+            "Scale factor: {}",
+            outer_radius as f64 * input.configs.resolution_x
+        ));
+        output.add_metadata_entry(format!("Standardized: {}", standardize));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Calculates the bathymetric position index of a DEM, i.e. the difference between each cell's
+/// value and the mean value of the cells within the annulus bounded by the inner and outer radii
+/// (in cells, inclusive). If `standardize` is true, the index is converted to a z-score,
+/// multiplied by 100, and rounded. Nodata cells in the returned array hold the nodata value of
+/// the input.
+pub(super) fn bathymetric_position_index(
+    input: &Arc<Raster>,
+    inner_radius: isize,
+    outer_radius: isize,
+    standardize: bool,
+    verbose: bool,
+    reporter: &dyn ProgressReporter,
+) -> Result<Array2D<f64>, Error> {
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+
+    // The annulus is scanned row-by-row using prefix sums of the values and valid cell
+    // counts along each row; each row of the annulus covers at most two column ranges.
+    let mut row_sums: Array2D<f64> = Array2D::new(rows, columns + 1, 0f64, nodata)?;
+    let mut row_counts: Array2D<f64> = Array2D::new(rows, columns + 1, 0f64, nodata)?;
+    for row in 0..rows {
+        let mut sum = 0f64;
+        let mut count = 0f64;
+        for col in 0..columns {
+            let z = input.get_value(row, col);
+            if !input.is_nodata(z) {
+                sum += z;
+                count += 1f64;
+            }
+            row_sums.set_value(row, col + 1, sum);
+            row_counts.set_value(row, col + 1, count);
+        }
+    }
+    let row_sums = Arc::new(row_sums);
+    let row_counts = Arc::new(row_counts);
+
+    // For each row offset, the half-widths of the outer disc and of the excluded inner disc.
+    let mut half_widths = vec![];
+    for dy in -outer_radius..=outer_radius {
+        let outer = ((outer_radius * outer_radius - dy * dy) as f64).sqrt().floor() as isize;
+        let mut inner = -1isize;
+        while (inner + 1) * (inner + 1) + dy * dy < inner_radius * inner_radius {
+            inner += 1;
+        }
+        half_widths.push((dy, outer, inner));
+    }
+    let half_widths = Arc::new(half_widths);
+
+    let configs = whitebox_common::configs::get_configs()?;
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let row_sums = row_sums.clone();
+        let row_counts = row_counts.clone();
+        let half_widths = half_widths.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            let mut z: f64;
+            let (mut sum, mut count): (f64, f64);
+            let mut r: isize;
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut data = vec![nodata; columns as usize];
+                for col in 0..columns {
+                    z = input.get_value(row, col);
+                    if input.is_nodata(z) {
+                        continue;
+                    }
+                    sum = 0f64;
+                    count = 0f64;
+                    for &(dy, outer, inner) in half_widths.iter() {
+                        r = row + dy;
+                        if r < 0 || r >= rows {
+                            continue;
+                        }
+                        if inner < 0 {
+                            // there is no inner disc in this row of the annulus
+                            add_range_sum(&row_sums, &row_counts, r, col - outer, col + outer, &mut sum, &mut count);
+                        } else {
+                            add_range_sum(&row_sums, &row_counts, r, col - outer, col - inner - 1, &mut sum, &mut count);
+                            add_range_sum(&row_sums, &row_counts, r, col + inner + 1, col + outer, &mut sum, &mut count);
+                        }
+                    }
+                    if count > 0f64 {
+                        data[col as usize] = z - sum / count;
+                    }
+                }
+                if tx.send((row, data)).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    let mut bpi: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+    let mut sum = 0f64;
+    let mut sq_sum = 0f64;
+    let mut n = 0f64;
+    for r in 0..rows {
+        let (row, data) = rx.recv().expect("Error receiving data from thread.");
+        for &z in &data {
+            if !is_nodata(z, nodata) {
+                sum += z;
+                sq_sum += z * z;
+                n += 1f64;
+            }
+        }
+        bpi.set_row_data(row, data);
+        reporter.check_cancelled()?;
+        if verbose {
+            progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Progress", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    if standardize && n > 0f64 {
+        let mean = sum / n;
+        let std_dev = (sq_sum / n - mean * mean).max(0f64).sqrt();
+        for row in 0..rows {
+            let mut data = bpi.get_row_data(row);
+            for z in data.iter_mut() {
+                if !is_nodata(*z, nodata) {
+                    *z = if std_dev > 0f64 {
+                        ((*z - mean) / std_dev * 100f64).round()
+                    } else {
+                        0f64
+                    };
+                }
+            }
+            bpi.set_row_data(row, data);
+        }
+    }
+
+    Ok(bpi)
+}
+
+/// Adds the sum and count of the valid cells of a row between two columns (inclusive) using
+/// the row's prefix sums. The column range is clipped to the raster.
+fn add_range_sum(
+    row_sums: &Array2D<f64>,
+    row_counts: &Array2D<f64>,
+    row: isize,
+    col1: isize,
+    col2: isize,
+    sum: &mut f64,
+    count: &mut f64,
+) {
+    let c1 = col1.max(0);
+    let c2 = col2.min(row_sums.columns() - 2);
+    if c2 >= c1 {
+        *sum += row_sums.get_value(row, c2 + 1) - row_sums.get_value(row, c1);
+        *count += row_counts.get_value(row, c2 + 1) - row_counts.get_value(row, c1);
+    }
+}
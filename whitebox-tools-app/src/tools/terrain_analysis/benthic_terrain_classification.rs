@@ -0,0 +1,538 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::bathymetric_position_index::bathymetric_position_index;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool classifies the seafloor of a bathymetric digital elevation model (`--dem`) into benthic
+/// terrain classes using a set of rules, contained in a classification dictionary file (`--dictionary`),
+/// that are applied to the fine- and broad-scale standardized bathymetric position index (BPI), slope,
+/// and depth of each grid cell. The approach follows the Benthic Terrain Modeler of Lundblad et al. (2006).
+///
+/// The fine- and broad-scale BPI are calculated internally using the annulus radii (in grid cells)
+/// specified by the `--fine_inner`, `--fine_outer`, `--broad_inner`, and `--broad_outer` parameters, and
+/// are standardized in the same way as the `BathymetricPositionIndex` tool, i.e. a value of 100 corresponds
+/// to one standard deviation above the mean BPI. Slope is measured in degrees using the 3x3 method of
+/// Horn (1981); for DEMs in geographic coordinates, the elevations are converted to the horizontal
+/// units using a latitude-dependent conversion factor. Depth is the DEM value itself, which for most
+/// bathymetric surfaces is negative below the datum.
+///
+/// The dictionary is a comma-separated values (CSV) file with a header line and one line per rule. The
+/// recognized columns, which may appear in any order, are:
+///
+/// | Column | Description |
+/// |-|-|
+/// | Class | Integer class value assigned in the output raster (required) |
+/// | Zone | Name of the class, e.g. 'Narrow depression' |
+/// | BroadBPI_Lower, BroadBPI_Upper | Bounds of the standardized broad-scale BPI |
+/// | FineBPI_Lower, FineBPI_Upper | Bounds of the standardized fine-scale BPI |
+/// | Slope_Lower, Slope_Upper | Bounds of the slope, in degrees |
+/// | Depth_Lower, Depth_Upper | Bounds of the depth |
+///
+/// Bounds are inclusive, and an empty bound is unbounded. The rules are evaluated in the order in which
+/// they appear in the file, and each cell is assigned the class of the first rule that it satisfies. For
+/// example, the following dictionary distinguishes crests, depressions, slopes, and flats:
+///
+/// ```text
+/// Class,Zone,BroadBPI_Lower,BroadBPI_Upper,FineBPI_Lower,FineBPI_Upper,Slope_Lower,Slope_Upper,Depth_Lower,Depth_Upper
+/// 1,Crest,100,,,,,,,
+/// 2,Depression,,-100,,,,,,
+/// 3,Slope,-100,100,,,5,,,
+/// 4,Flat,-100,100,,,,5,,
+/// ```
+///
+/// Cells that do not satisfy any rule are assigned a value of zero in the output raster, which is a
+/// categorical 16-bit integer raster; cells that are nodata in the DEM are nodata in the output. The number
+/// of cells assigned to each class is reported when the tool is run in verbose mode.
+///
+/// # Reference
+/// Horn, B. K. (1981). Hill shading and the reflectance map. *Proceedings of the IEEE*, 69(1), 14-47.
+///
+/// Lundblad, E. R., Wright, D. J., Miller, J., Larkin, E. M., Rinehart, R., Naar, D. F., Donahue, B. T.,
+/// Anderson, S. M., and Battista, T. (2006). A benthic terrain classification scheme for American Samoa.
+/// *Marine Geodesy*, 29(2), 89-111.
+///
+/// # See Also
+/// `BathymetricPositionIndex`, `Geomorphons`, `PennockLandformClass`, `Slope`
+pub struct BenthicTerrainClassification {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl BenthicTerrainClassification {
+    pub fn new() -> BenthicTerrainClassification {
+        // public constructor
+        let name = "BenthicTerrainClassification".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Classifies benthic terrain using rules based on fine and broad-scale BPI, slope, and depth."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster bathymetric DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Classification Dictionary File".to_owned(),
+            flags: vec!["--dictionary".to_owned()],
+            description: "Input CSV file containing the classification rules.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Fine-scale Inner Radius (cells)".to_owned(),
+            flags: vec!["--fine_inner".to_owned()],
+            description: "Inner radius of the fine-scale BPI annulus, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Fine-scale Outer Radius (cells)".to_owned(),
+            flags: vec!["--fine_outer".to_owned()],
+            description: "Outer radius of the fine-scale BPI annulus, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Broad-scale Inner Radius (cells)".to_owned(),
+            flags: vec!["--broad_inner".to_owned()],
+            description: "Inner radius of the broad-scale BPI annulus, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("3".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Broad-scale Outer Radius (cells)".to_owned(),
+            flags: vec!["--broad_outer".to_owned()],
+            description: "Outer radius of the broad-scale BPI annulus, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("25".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=bathy.tif --dictionary=btm_rules.csv -o=benthic_classes.tif --fine_inner=1 --fine_outer=3 --broad_inner=3 --broad_outer=25",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        BenthicTerrainClassification {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for BenthicTerrainClassification {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut dictionary_file = parsed.get_string("dictionary").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut fine_inner = parsed.get_i64("fine_inner").map(|v| v as isize).unwrap_or(1isize);
+        let fine_outer = parsed.get_i64("fine_outer").map(|v| v as isize).unwrap_or(3isize);
+        let mut broad_inner = parsed.get_i64("broad_inner").map(|v| v as isize).unwrap_or(3isize);
+        let broad_outer = parsed.get_i64("broad_outer").map(|v| v as isize).unwrap_or(25isize);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        dictionary_file = resolve_path(&dictionary_file, working_directory);
+        output_file = resolve_path(&output_file, working_directory);
+
+        fine_inner = fine_inner.max(0);
+        broad_inner = broad_inner.max(0);
+        if fine_outer < 1 || fine_outer <= fine_inner || broad_outer < 1 || broad_outer <= broad_inner {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Each outer radius must be at least one cell and larger than the corresponding inner radius.",
+            ));
+        }
+        if broad_outer <= fine_outer && verbose {
+            println!("Warning: The broad-scale outer radius is not larger than the fine-scale outer radius.");
+        }
+
+        let rules = Arc::new(read_dictionary(&dictionary_file)?);
+
+        if verbose {
+            println!("Reading data...")
+        };
+
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        if verbose {
+            println!("Calculating the fine-scale BPI...");
+        }
+        let fine_bpi = Arc::new(bathymetric_position_index(&input, fine_inner, fine_outer, true, verbose, reporter)?);
+        if verbose {
+            println!("Calculating the broad-scale BPI...");
+        }
+        let broad_bpi = Arc::new(bathymetric_position_index(&input, broad_inner, broad_outer, true, verbose, reporter)?);
+
+        if verbose {
+            println!("Classifying...");
+        }
+        let mut configs = input.configs.clone();
+        configs.data_type = DataType::I16;
+        configs.nodata = -32768f64;
+        configs.photometric_interp = PhotometricInterpretation::Categorical;
+        let mut output = Raster::initialize_using_config(&output_file, &configs);
+        let out_nodata = output.configs.nodata;
+
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let fine_bpi = fine_bpi.clone();
+            let broad_bpi = broad_bpi.clone();
+            let rules = rules.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+                let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+                let mut n: [f64; 8] = [0.0; 8];
+                let mut z: f64;
+                let (mut fx, mut fy): (f64, f64);
+                let mut slope: f64;
+                let mut z_factor: f64;
+                let eight_res_x = input.configs.resolution_x * 8.0;
+                let eight_res_y = input.configs.resolution_y * 8.0;
+                let is_geographic = input.is_in_geographic_coordinates();
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    z_factor = if is_geographic {
+                        // convert elevations to degrees at the latitude of the row
+                        1.0 / (111320.0 * input.get_y_from_row(row).to_radians().cos())
+                    } else {
+                        1.0
+                    };
+                    let mut data = vec![out_nodata; columns as usize];
+                    for col in 0..columns {
+                        z = input.get_value(row, col);
+                        if input.is_nodata(z) {
+                            continue;
+                        }
+                        for c in 0..8 {
+                            n[c] = input.get_value(row + d_y[c], col + d_x[c]);
+                            if input.is_nodata(n[c]) {
+                                n[c] = z;
+                            }
+                            n[c] *= z_factor;
+                        }
+                        fy = (n[6] - n[4] + 2.0 * (n[7] - n[3]) + n[0] - n[2]) / eight_res_y;
+                        fx = (n[2] - n[4] + 2.0 * (n[1] - n[5]) + n[0] - n[6]) / eight_res_x;
+                        slope = (fx * fx + fy * fy).sqrt().atan().to_degrees();
+
+                        let fine = fine_bpi.get_value(row, col);
+                        let broad = broad_bpi.get_value(row, col);
+                        if is_nodata(fine, nodata) || is_nodata(broad, nodata) {
+                            continue;
+                        }
+                        data[col as usize] = 0f64;
+                        for rule in rules.iter() {
+                            if rule.matches(broad, fine, slope, z) {
+                                data[col as usize] = rule.class as f64;
+                                break;
+                            }
+                        }
+                    }
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut class_counts = vec![0usize; rules.len()];
+        let mut num_unclassified = 0usize;
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            for &v in &data {
+                if v == 0f64 {
+                    num_unclassified += 1;
+                } else if !is_nodata(v, out_nodata) {
+                    if let Some(i) = rules.iter().position(|rule| rule.class as f64 == v) {
+                        class_counts[i] += 1;
+                    }
+                }
+            }
+            output.set_row_data(row, data);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.configs.palette = "qual.plt".to_string();
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Dictionary file: {}", dictionary_file));
+        output.add_metadata_entry(format!("Fine-scale BPI radii: {}-{}", fine_inner, fine_outer));
+        output.add_metadata_entry(format!("Broad-scale BPI radii: {}-{}", broad_inner, broad_outer));
+        for rule in rules.iter() {
+            output.add_metadata_entry(format!("Class {}: {}", rule.class, rule.zone));
+        }
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!("\nClass\tCells\tZone");
+            for (i, rule) in rules.iter().enumerate() {
+                println!("{}\t{}\t{}", rule.class, class_counts[i], rule.zone);
+            }
+            println!("0\t{}\tUnclassified", num_unclassified);
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// A benthic terrain classification rule. Unbounded limits are infinite.
+struct ClassRule {
+    class: i16,
+    zone: String,
+    broad_bpi: (f64, f64),
+    fine_bpi: (f64, f64),
+    slope: (f64, f64),
+    depth: (f64, f64),
+}
+
+impl ClassRule {
+    fn matches(&self, broad_bpi: f64, fine_bpi: f64, slope: f64, depth: f64) -> bool {
+        broad_bpi >= self.broad_bpi.0
+            && broad_bpi <= self.broad_bpi.1
+            && fine_bpi >= self.fine_bpi.0
+            && fine_bpi <= self.fine_bpi.1
+            && slope >= self.slope.0
+            && slope <= self.slope.1
+            && depth >= self.depth.0
+            && depth <= self.depth.1
+    }
+}
+
+/// Reads the classification rules from a dictionary CSV file.
+fn read_dictionary(file_name: &str) -> Result<Vec<ClassRule>, Error> {
+    let f = File::open(file_name)?;
+    let mut lines = BufReader::new(f).lines();
+    let header = match lines.next() {
+        Some(line) => line?,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The classification dictionary file is empty.",
+            ))
+        }
+    };
+    let fields: Vec<String> = header
+        .split(',')
+        .map(|s| s.trim().trim_start_matches('\u{feff}').to_lowercase())
+        .collect();
+    let column = |name: &str| fields.iter().position(|f| f == name);
+    let class_col = match column("class") {
+        Some(c) => c,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "The classification dictionary file does not contain a 'Class' column.",
+            ))
+        }
+    };
+    let zone_col = column("zone");
+    let bound_cols = [
+        (column("broadbpi_lower"), column("broadbpi_upper")),
+        (column("finebpi_lower"), column("finebpi_upper")),
+        (column("slope_lower"), column("slope_upper")),
+        (column("depth_lower"), column("depth_upper")),
+    ];
+
+    let mut rules = vec![];
+    for (line_num, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let values: Vec<&str> = line.split(',').map(|s| s.trim()).collect();
+        let value = |col: Option<usize>| col.and_then(|c| values.get(c)).map(|s| *s).unwrap_or("");
+        let parse_bound = |col: Option<usize>, unbounded: f64| -> Result<f64, Error> {
+            let s = value(col);
+            if s.is_empty() {
+                return Ok(unbounded);
+            }
+            s.parse::<f64>().map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Error parsing '{}' on line {} of the classification dictionary.", s, line_num + 2),
+                )
+            })
+        };
+        let class = value(Some(class_col)).parse::<f64>().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid class value on line {} of the classification dictionary.", line_num + 2),
+            )
+        })?;
+        if class < 1f64 || class > i16::MAX as f64 || class.fract() != 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Class values must be integers between 1 and {} (line {} of the classification dictionary).",
+                    i16::MAX,
+                    line_num + 2
+                ),
+            ));
+        }
+        let mut bounds = [(f64::NEG_INFINITY, f64::INFINITY); 4];
+        for i in 0..4 {
+            bounds[i] = (
+                parse_bound(bound_cols[i].0, f64::NEG_INFINITY)?,
+                parse_bound(bound_cols[i].1, f64::INFINITY)?,
+            );
+        }
+        rules.push(ClassRule {
+            class: class as i16,
+            zone: value(zone_col).to_string(),
+            broad_bpi: bounds[0],
+            fine_bpi: bounds[1],
+            slope: bounds[2],
+            depth: bounds[3],
+        });
+    }
+
+    if rules.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "The classification dictionary file does not contain any rules.",
+        ));
+    }
+    Ok(rules)
+}
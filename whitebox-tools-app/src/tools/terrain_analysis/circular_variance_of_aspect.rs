@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to calculate the circular variance (i.e. one minus the mean resultant length) of aspect
 /// for an input digital elevation model (DEM). This is a measure of how variable slope aspect is within a local
@@ -64,7 +63,7 @@ impl CircularVarianceOfAspect {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -73,7 +72,7 @@ impl CircularVarianceOfAspect {
 
         parameters.push(ToolParameter {
             name: "Output Raster File".to_owned(),
-            flags: vec!["--output".to_owned()],
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
             description: "Output raster file.".to_owned(),
             parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
             default_value: None,
@@ -102,7 +101,7 @@ impl CircularVarianceOfAspect {
         if e.contains(".exe") {
             short_exe += ".exe";
         }
-        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --out_mag=roughness_mag.tif --out_scale=roughness_scale.tif --min_scale=1 --max_scale=1000 --step=5", short_exe, name).replace("*", &sep);
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=output.tif --filter=11", short_exe, name).replace("*", &sep);
 
         CircularVarianceOfAspect {
             name: name,
@@ -154,52 +153,12 @@ impl WhiteboxTool for CircularVarianceOfAspect {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size = 11usize;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-filter" {
-                filter_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(11usize);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -212,8 +171,6 @@ impl WhiteboxTool for CircularVarianceOfAspect {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         if filter_size < 3 {
             filter_size = 3;
         }
@@ -234,12 +191,8 @@ impl WhiteboxTool for CircularVarianceOfAspect {
             ));
         }
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -311,12 +264,8 @@ impl WhiteboxTool for CircularVarianceOfAspect {
             let d_y = Arc::new(d_y);
             let weights = Arc::new(weights);
 
-            let mut num_procs = num_cpus::get() as isize;
             let configs = whitebox_common::configs::get_configs()?;
-            let max_procs = configs.max_procs;
-            if max_procs > 0 && max_procs < num_procs {
-                num_procs = max_procs;
-            }
+            let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
             let (tx, rx) = mpsc::channel();
             for tid in 0..num_procs {
                 let input = input.clone();
@@ -324,7 +273,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                 let d_y = d_y.clone();
                 let weights = weights.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut sum, mut z_final): (f64, f64);
                     let mut z: f64;
                     let mut zn: f64;
@@ -333,14 +282,14 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 sum = 0.0;
                                 z_final = 0.0;
                                 for a in 0..num_pixels_in_filter {
                                     x = col + d_x[a];
                                     y = row + d_y[a];
                                     zn = input.get_value(y, x);
-                                    if zn != nodata {
+                                    if !is_nodata(zn, nodata) {
                                         sum += weights[a];
                                         z_final += weights[a] * zn;
                                     }
@@ -349,7 +298,9 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                             }
                         }
 
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -404,7 +355,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                         sum_n = 0;
                         for col in 0..columns {
                             val = input.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata(val, nodata) {
                                 val = 0f64;
                             } else {
                                 sum_n += 1;
@@ -428,7 +379,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                         sum = 0f64;
                         for col in 0..columns {
                             val = smoothed_dem.get_value(row, col);
-                            if val == nodata {
+                            if is_nodata(val, nodata) {
                                 val = 0f64;
                             }
                             sum += val;
@@ -454,7 +405,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                     }
 
                     for col in 0..columns {
-                        if input.get_value(row, col) != nodata {
+                        if !is_nodata(input.get_value(row, col), nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -497,18 +448,14 @@ impl WhiteboxTool for CircularVarianceOfAspect {
             }
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
         let smoothed_dem = Arc::new(smoothed_dem);
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let smoothed_dem = smoothed_dem.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let mut n: [f64; 8] = [0.0; 8];
@@ -519,10 +466,10 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                     let mut ydata = vec![0f64; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             for c in 0..8 {
                                 n[c] = smoothed_dem.get_value(row + dy[c], col + dx[c]);
-                                if n[c] != nodata {
+                                if !is_nodata(n[c], nodata) {
                                     n[c] = n[c] * z_factor;
                                 } else {
                                     n[c] = z * z_factor;
@@ -541,7 +488,9 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                             }
                         }
                     }
-                    tx.send((row, xdata, ydata)).unwrap();
+                    if tx.send((row, xdata, ydata)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -552,10 +501,11 @@ impl WhiteboxTool for CircularVarianceOfAspect {
             let data = rx.recv().expect("Error receiving data from thread.");
             xc.set_row_data(data.0, data.1);
             yc.set_row_data(data.0, data.2);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating aspect data: {}%", progress);
+                    reporter.progress("Calculating aspect data", progress);
                     old_progress = progress;
                 }
             }
@@ -573,7 +523,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                 for col in 0..columns {
                     sumx += xc.get_value(row, col);
                     sumy += yc.get_value(row, col);
-                    if smoothed_dem.get_value(row, col) == nodata
+                    if is_nodata(smoothed_dem.get_value(row, col), nodata)
                         || (xc.get_value(row, col) == 0f64 && yc.get_value(row, col) == 0f64)
                     {
                         // it's either nodata or a flag cell in the DEM.
@@ -585,14 +535,14 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                     i_n.set_value(row, col, sumn + i_n.get_value(row - 1, col));
                 }
             } else {
-                if smoothed_dem.get_value(0, 0) == nodata {
+                if is_nodata(smoothed_dem.get_value(0, 0), nodata) {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
                     xc.increment(row, col, xc.get_value(row, col - 1));
                     yc.increment(row, col, yc.get_value(row, col - 1));
                     i_n.increment(row, col, i_n.get_value(row, col - 1));
-                    if smoothed_dem.get_value(row, col) == nodata
+                    if is_nodata(smoothed_dem.get_value(row, col), nodata)
                         || (xc.get_value(row, col) == 0f64 && yc.get_value(row, col) == 0f64)
                     {
                         // it's either nodata or a flag cell in the DEM.
@@ -600,10 +550,11 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
+                    reporter.progress("Creating integral images", progress);
                     old_progress = progress;
                 }
             }
@@ -619,7 +570,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
             let smoothed_dem = smoothed_dem.clone();
             let i_n = i_n.clone();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
                 let mut n: f64;
                 let (mut sumx, mut sumy): (f64, f64);
@@ -644,7 +595,7 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = smoothed_dem.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -679,14 +630,8 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                         }
                     }
 
-                    match tx2.send((row, data)) {
-                        Ok(_) => {}
-                        Err(_) => {
-                            println!(
-                                "Error sending data from thread {} processing row {}.",
-                                tid, row
-                            );
-                        }
+                    if tx2.send((row, data)).is_err() {
+                        return;
                     }
                 }
             });
@@ -709,10 +654,11 @@ impl WhiteboxTool for CircularVarianceOfAspect {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    reporter.progress("Performing analysis", progress);
                     old_progress = progress;
                 }
             }
@@ -6,21 +6,20 @@ Last Modified: 04/03/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::structures::{Array2D, Point2D};
 use crate::tools::*;
 use whitebox_vector::*;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 const EPSILON: f64 = std::f64::EPSILON;
 use kdtree::distance::squared_euclidean;
 use kdtree::KdTree;
-use num_cpus;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to create a vector contour coverage from an input raster surface model (`--input`), such as a digital
 /// elevation model (DEM). The user must specify the contour interval (`--interval`) and optionally, the base contour value (`--base`).
@@ -51,7 +50,7 @@ impl ContoursFromRaster {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input Raster Surface File".to_owned(),
-            flags: vec!["-i".to_owned(), "--input".to_owned()],
+            flags: vec!["--dem".to_owned(), "-i".to_owned(), "--input".to_owned()],
             description: "Input surface raster file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -176,103 +175,33 @@ impl WhiteboxTool for ContoursFromRaster {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut contour_interval = 10f64;
-        let mut base_contour = 0f64;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let contour_interval = parsed.get_f64("interval").unwrap_or(10f64);
+        let base_contour = parsed.get_f64("base").unwrap_or(0f64);
         let mut deflection_tolerance = 10f64;
         let mut filter_size = 9;
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_f64("tolerance") {
+            deflection_tolerance = value;
+            if deflection_tolerance < 0f64 {
+                deflection_tolerance = 0f64;
+            }
+            if deflection_tolerance > 45f64 {
+                deflection_tolerance = 45f64;
+            }
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_usize("smooth")? {
+            filter_size = value;
+            if filter_size > 21 {
+                filter_size = 21;
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-interval" {
-                contour_interval = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-base" {
-                base_contour = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-tolerance" {
-                deflection_tolerance = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if deflection_tolerance < 0f64 {
-                    deflection_tolerance = 0f64;
-                }
-                if deflection_tolerance > 45f64 {
-                    deflection_tolerance = 45f64;
-                }
-            } else if flag_val == "-smooth" {
-                filter_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if filter_size > 21 {
-                    filter_size = 21;
-                }
-                if filter_size % 2 == 0 && filter_size != 0 {
-                    // must be an odd integer.
-                    filter_size += 1;
-                }
+            if filter_size % 2 == 0 && filter_size != 0 {
+                // must be an odd integer.
+                filter_size += 1;
             }
         }
 
@@ -294,14 +223,8 @@ impl WhiteboxTool for ContoursFromRaster {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -345,28 +268,26 @@ impl WhiteboxTool for ContoursFromRaster {
         let dy = [-1, 0, 1, 0, -1, 1, 1, -1];
 
         // Reclass the input raster
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             data[col as usize] = ((z - base_contour) / contour_interval).floor();
                         }
                     }
-                    tx.send((row, data)).expect("Error sending data to thread.");
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -376,10 +297,11 @@ impl WhiteboxTool for ContoursFromRaster {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             reclassed.set_row_data(row, data);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Reclassifying surface: {}%", progress);
+                    reporter.progress("Reclassifying surface", progress);
                     old_progress = progress;
                 }
             }
@@ -429,10 +351,10 @@ impl WhiteboxTool for ContoursFromRaster {
         for row in 0..rows {
             for col in 0..columns {
                 z = reclassed.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z, nodata) {
                     for n in 0..4 {
                         zn = reclassed.get_value(row + dy[n], col + dx[n]);
-                        if z > zn && zn != nodata {
+                        if z > zn && !is_nodata(zn, nodata) {
                             z1 = zn as isize + 1;
                             z2 = z as isize;
                             for contour_val in z1..=z2 {
@@ -462,10 +384,11 @@ impl WhiteboxTool for ContoursFromRaster {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding edges: {}%", progress);
+                    reporter.progress("Finding edges", progress);
                     old_progress = progress;
                 }
             }
@@ -703,11 +626,12 @@ impl WhiteboxTool for ContoursFromRaster {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * line_segment as f64 / (line_segments.len() - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Tracing contours (Loop 1 of 2): {}%", progress);
+                    reporter.progress("Tracing contours (Loop 1 of 2)", progress);
                     old_progress = progress;
                 }
             }
@@ -907,11 +831,12 @@ impl WhiteboxTool for ContoursFromRaster {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * line_segment as f64 / (line_segments.len() - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Tracing contours (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Tracing contours (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
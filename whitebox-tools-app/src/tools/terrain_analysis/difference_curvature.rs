@@ -0,0 +1,251 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::partial_derivatives::calculate_curvature;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::io::Error;
+use std::path;
+use std::sync::Arc;
+use whitebox_common::utils::get_formatted_elapsed_time;
+
+/// This tool calculates the difference curvature from a digital elevation model (DEM). Difference curvature
+/// is half of the difference between the vertical (profile) and horizontal (tangential) curvatures, and compares
+/// the relative contributions of the two flow mechanisms, i.e. relative deceleration and convergence, to flow
+/// accumulation at a given point on the topographic surface (Florinsky, 2017). Difference curvature is measured
+/// in units of m<sup>-1</sup>.
+/// 
+/// The user must specify the name of the input DEM (`--dem`) and the output raster (`--output`).
+/// The Z conversion factor (`--zfactor`) is only important when the vertical and horizontal units are not the
+/// same in the DEM. When this is the case, the algorithm will multiply each elevation in the DEM by the
+/// Z Conversion Factor. Curvature values are often very small and as such the user may opt to log-transform
+/// the output raster (`--log`). Transforming the values applies the equation by Shary et al. (2002):
+/// 
+/// *Θ*' = sign(*Θ*) ln(1 + 10<sup>*n*</sup>|*Θ*|) 
+/// 
+/// where *Θ* is the parameter value and *n* is dependent on the grid cell size.
+/// 
+/// For DEMs in projected coordinate systems, the tool uses the 3rd-order bivariate 
+/// Taylor polynomial method described by Florinsky (2016). Based on a polynomial fit 
+/// of the elevations within the 5x5 neighbourhood surrounding each cell, this method is considered more 
+/// robust against outlier elevations (noise) than other methods. For DEMs in geographic coordinate systems
+/// (i.e. angular units), the tool uses the 3x3 polynomial fitting method for equal angle grids also 
+/// described by Florinsky (2016). 
+///
+/// # References
+/// Florinsky, I. (2016). Digital terrain analysis in soil science and geology. Academic Press.
+/// 
+/// Florinsky, I. V. (2017). An illustrated introduction to general geomorphometry. Progress in Physical 
+/// Geography, 41(6), 723-752.
+/// 
+/// Shary P. A., Sharaya L. S. and Mitusov A. V. (2002) Fundamental quantitative methods of land surface analysis. 
+/// Geoderma 107: 1–32.
+///
+/// # See Also
+/// `TangentialCurvature`, `ProfileCurvature`, `MinimalCurvature`, `RingCurvature`, `Rotor`, `HorizontalExcessCurvature`, `VerticalExcessCurvature`, `AccumulationCurvature`
+pub struct DifferenceCurvature {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl DifferenceCurvature {
+    pub fn new() -> DifferenceCurvature {
+        // public constructor
+        let name = "DifferenceCurvature".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Calculates a difference curvature raster from an input DEM.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Log-transform the output?".to_owned(),
+            flags: vec!["--log".to_owned()],
+            description: "Display output values using a log-scale."
+                .to_owned(),
+            parameter_type: ParameterType::Boolean,
+            default_value: Some("false".to_string()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Z Conversion Factor".to_owned(),
+            flags: vec!["--zfactor".to_owned()],
+            description:
+                "Optional multiplier for when the vertical and horizontal units are not the same."
+                    .to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=output.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        DifferenceCurvature {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for DifferenceCurvature {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let tool_name = self.get_tool_name();
+
+        // read the arguments
+        let mut input_file: String = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file: String = parsed.get_string("output").unwrap_or_default();
+        let log_transform = parsed.get_bool("log");
+        let z_factor = parsed.get_f64("zfactor").unwrap_or(1f64);
+
+        if verbose {
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28); 
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let start = Instant::now();
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        // Read in the input raster
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        calculate_curvature(
+            &input,
+            &mut output,
+            z_factor,
+            log_transform,
+            |d| d.difference_curvature(),
+            verbose,
+            reporter,
+        )?;
+
+        //////////////////////
+        // Output the image //
+        //////////////////////
+        if verbose {
+            println!("Saving data...")
+        };
+        
+        let elapsed_time = get_formatted_elapsed_time(start);
+        
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            tool_name
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
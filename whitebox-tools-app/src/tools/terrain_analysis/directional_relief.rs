@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool calculates the relief for each grid cell in a digital elevation model (DEM) in a specified direction.
 /// Directional relief is an index of the degree to which a DEM grid cell is higher or lower than its surroundings.
@@ -64,7 +63,7 @@ impl DirectionalRelief {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -164,66 +163,13 @@ impl WhiteboxTool for DirectionalRelief {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut azimuth = 0.0;
-        let mut max_dist = f64::INFINITY;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-azimuth" {
-                if keyval {
-                    azimuth = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    azimuth = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-max_dist" {
-                if keyval {
-                    max_dist = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    max_dist = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut azimuth = parsed.get_f64("azimuth").unwrap_or(0.0);
+        let mut max_dist = parsed.get_f64("max_dist").unwrap_or(f64::INFINITY);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -236,17 +182,11 @@ impl WhiteboxTool for DirectionalRelief {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -303,17 +243,13 @@ impl WhiteboxTool for DirectionalRelief {
             y_step = 1;
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut current_val: f64;
                 let mut y_intercept: f64;
@@ -337,7 +273,7 @@ impl WhiteboxTool for DirectionalRelief {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         current_val = input[(row, col)];
-                        if current_val != nodata {
+                        if !is_nodata(current_val, nodata) {
                             total_elevation = 0f64;
                             n_elevations = 0f64;
 
@@ -363,7 +299,7 @@ impl WhiteboxTool for DirectionalRelief {
                                         y2 = y1 + y_step * -1isize;
                                         z1 = input[(y1, x as isize)];
                                         z2 = input[(y2, x as isize)];
-                                        if z1 != nodata && z2 != nodata {
+                                        if !is_nodata(z1, nodata) && !is_nodata(z2, nodata) {
                                             z = z1 + (y - y1 as f64) * (z2 - z1);
                                             total_elevation += z;
                                             n_elevations += 1f64;
@@ -403,7 +339,7 @@ impl WhiteboxTool for DirectionalRelief {
                                         } else {
                                             z1 = input[(-y as isize, x1)];
                                             z2 = input[(y as isize, x2)];
-                                            if z1 != nodata && z2 != nodata {
+                                            if !is_nodata(z1, nodata) && !is_nodata(z2, nodata) {
                                                 z = z1 + (x - x1 as f64) * (z2 - z1);
                                                 total_elevation += z;
                                                 n_elevations += 1f64;
@@ -429,7 +365,9 @@ impl WhiteboxTool for DirectionalRelief {
                             }
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -442,10 +380,11 @@ impl WhiteboxTool for DirectionalRelief {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
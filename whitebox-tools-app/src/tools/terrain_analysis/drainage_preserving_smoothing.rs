@@ -14,7 +14,6 @@ Testing showed that it was not substantially better than the FeaturePreservingSm
 use whitebox_raster::Raster;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::i64;
@@ -24,7 +23,7 @@ use std::ops::SubAssign;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool implements a modified form of the algorithm described by
 /// Sun, Rosin, Martin, and Langbein (2007) '*Fast and effective feature-preserving
@@ -54,7 +53,7 @@ impl DrainagePreservingSmoothing {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -84,7 +83,7 @@ impl DrainagePreservingSmoothing {
             flags: vec!["--norm_diff".to_owned()],
             description: "Maximum difference in normal vectors, in degrees.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("15.0".to_owned()),
+            default_value: Some("8.0".to_owned()),
             optional: true,
         });
 
@@ -102,7 +101,7 @@ impl DrainagePreservingSmoothing {
             flags: vec!["--max_diff".to_owned()],
             description: "Maximum allowable absolute elevation change (optional).".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("0.5".to_owned()),
+            default_value: None,
             optional: true,
         });
 
@@ -207,90 +206,26 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size = 11usize;
-        let mut max_norm_diff = 8f64;
-        let mut num_iter = 3;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(11usize);
+        let mut max_norm_diff = parsed.get_f64("norm_diff").unwrap_or(8f64);
+        let mut num_iter = parsed.get_usize("num_iter")?.unwrap_or(3);
         let mut reduction = 80f64;
         let mut dfm_threshold = 0.15;
-        let mut z_factor = -1f64;
-        let mut max_z_diff = f64::INFINITY;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        let mut z_factor = parsed.get_f64("zfactor").unwrap_or(-1f64);
+        let mut max_z_diff = parsed.get_f64("max_diff").unwrap_or(f64::INFINITY);
+
+        if let Some(value) = parsed.get_f64("reduction") {
+            reduction = value;
+            reduction = reduction.abs();
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-filter" {
-                filter_size = if keyval {
-                    vec[1].to_string().parse::<f32>().expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1].to_string().parse::<f32>().expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-norm_diff" {
-                max_norm_diff = if keyval {
-                    vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_iter" {
-                num_iter = if keyval {
-                    vec[1].to_string().parse::<f32>().expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1].to_string().parse::<f32>().expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-reduction" {
-                reduction = if keyval {
-                    vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                };
-                reduction = reduction.abs();
-            } else if flag_val == "-dfm" {
-                dfm_threshold = if keyval {
-                    vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                };
-                dfm_threshold = dfm_threshold.abs();
-            } else if flag_val == "-zfactor" {
-                z_factor = if keyval {
-                    vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_diff" {
-                max_z_diff = if keyval {
-                    vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                };
-            }
+        if let Some(value) = parsed.get_f64("dfm") {
+            dfm_threshold = value;
+            dfm_threshold = dfm_threshold.abs();
         }
 
         if verbose {
@@ -332,12 +267,8 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -377,29 +308,27 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
         let midpoint = filter_size as isize; // The dfm filter is twice the size of the smoothing filter.
                                              // let midpoint = (filter_size as f64 / 2f64).floor() as isize;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut val: i64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![bin_nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             val = (z * multiplier).floor() as i64 - min_bin;
                             data[col as usize] = val;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -407,10 +336,11 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             binned_data.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Binning elevations: {}%", progress);
+                    reporter.progress("Binning elevations", progress);
                     old_progress = progress;
                 }
             }
@@ -421,7 +351,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
         for tid in 0..num_procs {
             let binned_data = bd.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut bin_val, mut bin_val_n): (i64, i64);
                 let (mut start_col, mut end_col, mut start_row, mut end_row): (
                     isize,
@@ -443,12 +373,12 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         bin_val = binned_data.get_value(row, col);
-                        if bin_val != bin_nodata {
-                            if old_median != bin_nodata {
+                        if !is_nodata(bin_val, bin_nodata) {
+                            if !is_nodata(old_median, bin_nodata) {
                                 // remove the trailing column from the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col - midpoint - 1);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] -= 1;
                                         n -= 1.0;
                                         if bin_val_n < old_median {
@@ -460,7 +390,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                                 // add the leading column to the histo
                                 for row2 in start_row..end_row + 1 {
                                     bin_val_n = binned_data.get_value(row2, col + midpoint);
-                                    if bin_val_n != bin_nodata {
+                                    if !is_nodata(bin_val_n, bin_nodata) {
                                         histo[bin_val_n as usize] += 1;
                                         n += 1.0;
                                         if bin_val_n < old_median {
@@ -502,7 +432,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                                 for col2 in start_col..end_col + 1 {
                                     for row2 in start_row..end_row + 1 {
                                         bin_val_n = binned_data.get_value(row2, col2);
-                                        if bin_val_n != bin_nodata {
+                                        if !is_nodata(bin_val_n, bin_nodata) {
                                             histo[bin_val_n as usize] += 1;
                                             n += 1f64;
                                         }
@@ -533,7 +463,9 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                             old_median = bin_nodata;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -542,10 +474,11 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             dfm_data.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating topographic position: {}%", progress);
+                    reporter.progress("Calculating topographic position", progress);
                     old_progress = progress;
                 }
             }
@@ -554,18 +487,14 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
         ///////////////////////////////
         // Create the normal vectors //
         ///////////////////////////////
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             // let exclusions = exclusions.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let eight_grid_res = input.configs.resolution_x as f32 * 8f32;
@@ -584,10 +513,10 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                     let mut values = [0f32; 9];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             for i in 0..8 {
                                 zn = input.get_value(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata(zn, nodata) {
                                     values[i] = (zn * z_factor) as f32;
                                 } else {
                                     values[i] = (z * z_factor) as f32;
@@ -608,7 +537,9 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                             };
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -623,10 +554,11 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
             let data = rx.recv().expect("Error receiving data from thread.");
             nv.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating normal vectors: {}%", progress);
+                    reporter.progress("Calculating normal vectors", progress);
                     old_progress = progress;
                 }
             }
@@ -654,7 +586,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
             let nv = nv.clone();
             let dfm_data = dfm_data.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let num_pixels_in_filter = filter_size * filter_size;
                 let mut dx = vec![0isize; num_pixels_in_filter];
                 let mut dy = vec![0isize; num_pixels_in_filter];
@@ -698,7 +630,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                         } else {
                             threshold as f32
                         };
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             sum_w = 0f32;
                             a = 0f32;
                             b = 0f32;
@@ -706,7 +638,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                             for n in 0..num_pixels_in_filter {
                                 xn = col + dx[n];
                                 yn = row + dy[n];
-                                if input.get_value(yn, xn) != nodata {
+                                if !is_nodata(input.get_value(yn, xn), nodata) {
                                     //     && exclusions.get_value(yn, xn) == 0f64
                                     // {
                                     diff =
@@ -727,7 +659,9 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                             data[col as usize] = Normal { a: a, b: b, c: c };
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -737,10 +671,11 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
             let data = rx.recv().expect("Error receiving data from thread.");
             nv_smooth.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Smoothing normal vectors: {}%", progress);
+                    reporter.progress("Smoothing normal vectors", progress);
                     old_progress = progress;
                 }
             }
@@ -786,7 +721,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
             for row in 0..rows {
                 for col in 0..columns {
                     z = output.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         dfm_value = dfm_data.get_value(row, col);
                         threshold_adj = if dfm_value < 0f64 && dfm_value > dfm_threshold {
                             (max_norm_diff * (1f64 - reduction * dfm_value / dfm_threshold))
@@ -803,7 +738,7 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                             xn = col + dx[n];
                             yn = row + dy[n];
                             zn = output.get_value(yn, xn);
-                            if zn != nodata {
+                            if !is_nodata(zn, nodata) {
                                 //&& exclusions.get_value(yn, xn) == 0f64 {
                                 diff = nv_smooth
                                     .get_value(row, col)
@@ -833,15 +768,11 @@ impl WhiteboxTool for DrainagePreservingSmoothing {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Updating DEM elevations (Loop {} of {}): {}%",
-                            loop_num + 1,
-                            num_iter,
-                            progress
-                        );
+                        reporter.progress(&format!("Updating DEM elevations (Loop {} of {})", loop_num + 1, num_iter), progress);
                         old_progress = progress;
                     }
                 }
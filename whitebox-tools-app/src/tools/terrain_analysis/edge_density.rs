@@ -9,7 +9,6 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
@@ -18,7 +17,7 @@ use std::ops::SubAssign;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool calculates the density of edges, or breaks-in-slope within an input digital elevation model (DEM).
 /// A break-in-slope occurs between two neighbouring grid cells if the angular difference between their normal
@@ -54,7 +53,7 @@ impl EdgeDensity {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -167,79 +166,14 @@ impl WhiteboxTool for EdgeDensity {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size = 11usize;
-        let mut max_norm_diff = 5f64;
-        let mut z_factor = -1f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-filter" {
-                filter_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-norm_diff" {
-                max_norm_diff = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-zfactor" {
-                z_factor = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(11usize);
+        let mut max_norm_diff = parsed.get_f64("norm_diff").unwrap_or(5f64);
+        let mut z_factor = parsed.get_f64("zfactor").unwrap_or(-1f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -268,17 +202,11 @@ impl WhiteboxTool for EdgeDensity {
         }
         let threshold = max_norm_diff.to_radians().cos();
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -307,17 +235,13 @@ impl WhiteboxTool for EdgeDensity {
         ///////////////////////////////
         // Create the normal vectors //
         ///////////////////////////////
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let eight_grid_res = input.configs.resolution_x as f32 * 8f32;
@@ -336,10 +260,10 @@ impl WhiteboxTool for EdgeDensity {
                     let mut values = [0f32; 9];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             for i in 0..8 {
                                 zn = input.get_value(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata(zn, nodata) {
                                     values[i] = (zn * z_factor) as f32;
                                 } else {
                                     values[i] = (z * z_factor) as f32;
@@ -360,7 +284,9 @@ impl WhiteboxTool for EdgeDensity {
                             };
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -375,10 +301,11 @@ impl WhiteboxTool for EdgeDensity {
             let data = rx.recv().expect("Error receiving data from thread.");
             nv.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating normal vectors: {}%", progress);
+                    reporter.progress("Calculating normal vectors", progress);
                     old_progress = progress;
                 }
             }
@@ -393,7 +320,7 @@ impl WhiteboxTool for EdgeDensity {
             let nv = nv.clone();
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let mut z: f64;
@@ -405,12 +332,12 @@ impl WhiteboxTool for EdgeDensity {
                     let mut data = vec![0f64; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             centre_norm = nv.get_value(row, col);
                             edge_found = false;
                             for c in 0..8 {
                                 zn = input.get_value(row + dy[c], col + dx[c]);
-                                if zn != nodata {
+                                if !is_nodata(zn, nodata) {
                                     neighbour_norm = nv.get_value(row + dy[c], col + dx[c]);
                                     if centre_norm.angle_between(neighbour_norm) <= threshold32 {
                                         edge_found = true;
@@ -424,7 +351,9 @@ impl WhiteboxTool for EdgeDensity {
                             }
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -434,10 +363,11 @@ impl WhiteboxTool for EdgeDensity {
             let data = rx.recv().expect("Error receiving data from thread.");
             edges.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding edges: {}%", progress);
+                    reporter.progress("Finding edges", progress);
                     old_progress = progress;
                 }
             }
@@ -454,28 +384,29 @@ impl WhiteboxTool for EdgeDensity {
                 for col in 0..columns {
                     sum += edges.get_value(row, col);
                     edges.set_value(row, col, sum + edges.get_value(row - 1, col));
-                    if input.get_value(row, col) == nodata {
+                    if is_nodata(input.get_value(row, col), nodata) {
                         i_n.decrement(row, col, 1);
                     }
                     sumn += i_n.get_value(row, col);
                     i_n.set_value(row, col, sumn + i_n.get_value(row - 1, col));
                 }
             } else {
-                if input.get_value(0, 0) == nodata {
+                if is_nodata(input.get_value(0, 0), nodata) {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
                     edges.increment(row, col, edges.get_value(row, col - 1));
                     i_n.increment(row, col, i_n.get_value(row, col - 1));
-                    if input.get_value(row, col) == nodata {
+                    if is_nodata(input.get_value(row, col), nodata) {
                         i_n.decrement(row, col, 1);
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
+                    reporter.progress("Creating integral images", progress);
                     old_progress = progress;
                 }
             }
@@ -489,7 +420,7 @@ impl WhiteboxTool for EdgeDensity {
             let i_n = i_n.clone();
             let input = input.clone();
             let tx2 = tx2.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
                 let mut n: f64;
                 let mut sum: f64;
@@ -508,7 +439,7 @@ impl WhiteboxTool for EdgeDensity {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             x1 = col - midpoint - 1;
                             if x1 < 0 {
                                 x1 = 0;
@@ -531,14 +462,8 @@ impl WhiteboxTool for EdgeDensity {
                         }
                     }
 
-                    match tx2.send((row, data)) {
-                        Ok(_) => {}
-                        Err(_) => {
-                            println!(
-                                "Error sending data from thread {} processing row {}.",
-                                tid, row
-                            );
-                        }
+                    if tx2.send((row, data)).is_err() {
+                        return;
                     }
                 }
             });
@@ -561,10 +486,11 @@ impl WhiteboxTool for EdgeDensity {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    reporter.progress("Performing analysis", progress);
                     old_progress = progress;
                 }
             }
@@ -6,16 +6,15 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to express the elevation of a grid cell in a digital elevation model (DEM) as a percentage
 /// of the relief between the watershed minimum and maximum values. As such, it provides a basic
@@ -42,7 +41,7 @@ impl ElevRelativeToWatershedMinMax {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -132,51 +131,12 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut watersheds_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i"
-                || vec[0].to_lowercase() == "--input"
-                || vec[0].to_lowercase() == "--dem"
-            {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-watersheds"
-                || vec[0].to_lowercase() == "--watersheds"
-            {
-                if keyval {
-                    watersheds_file = vec[1].to_string();
-                } else {
-                    watersheds_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut watersheds_file = parsed.get_string("watersheds").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -189,20 +149,12 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !watersheds_file.contains(&sep) && !watersheds_file.contains("/") {
-            watersheds_file = format!("{}{}", working_directory, watersheds_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        watersheds_file = resolve_input_path(&watersheds_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -221,11 +173,9 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
         if watersheds.configs.rows != input.configs.rows
             || watersheds.configs.columns != input.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&watersheds, &input, verbose);
 
         let start = Instant::now();
 
@@ -235,27 +185,28 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
         let max_watershed = watersheds.configs.maximum;
         let range_watersheds = max_watershed - min_watershed;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let input = input.clone();
             let watersheds = watersheds.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut watershed_min_vals = vec![f64::INFINITY; range_watersheds as usize + 1];
                 let mut watershed_max_vals = vec![f64::NEG_INFINITY; range_watersheds as usize + 1];
                 let mut z: f64;
                 let mut watershed: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     for col in 0..columns {
                         z = input[(row, col)];
                         watershed = watersheds[(row, col)];
-                        if z != nodata && watershed != watershed_nodata {
+                        if !is_nodata(z, nodata) && !is_nodata(watershed, watershed_nodata) {
                             watershed -= min_watershed;
                             if z < watershed_min_vals[watershed as usize] {
                                 watershed_min_vals[watershed as usize] = z;
@@ -266,7 +217,9 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
                         }
                     }
                 }
-                tx.send((watershed_min_vals, watershed_max_vals)).unwrap();
+                if tx.send((watershed_min_vals, watershed_max_vals)).is_err() {
+                    return;
+                }
             });
         }
 
@@ -283,10 +236,11 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
                     watershed_max_vals[i] = maxs[i];
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * tid as f64 / (num_procs - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -299,7 +253,7 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
             let watershed_min_vals = watershed_min_vals.clone();
             let watershed_max_vals = watershed_max_vals.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut watershed: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -307,7 +261,7 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
                     for col in 0..columns {
                         z = input[(row, col)];
                         watershed = watersheds[(row, col)];
-                        if z != nodata && watershed != watershed_nodata {
+                        if !is_nodata(z, nodata) && !is_nodata(watershed, watershed_nodata) {
                             watershed -= min_watershed;
                             data[col as usize] = (z - watershed_min_vals[watershed as usize])
                                 / (watershed_max_vals[watershed as usize]
@@ -315,7 +269,9 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
                                 * 100f64;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -323,10 +279,11 @@ impl WhiteboxTool for ElevRelativeToWatershedMinMax {
         for r in 0..rows {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -6,7 +6,7 @@ Last Modified: 05/10/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::structures::{Array2D, BoundingBox, DistanceMetric, FixedRadiusSearch2D};
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
@@ -61,7 +61,7 @@ impl EmbankmentMapping {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -184,7 +184,7 @@ impl EmbankmentMapping {
             short_exe += ".exe";
         }
         let usage = format!(
-            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif -o=output.tif --search_dist=1.0 --min_road_width=6.0 --typical_width=30.0 --max_height=2.0 --max_width=60.0 --max_increment=0.05 --spillout_slope=4.0 --remove_embankments=true",
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" -i=DEM.tif --road_vec=roads.shp -o=output.tif --search_dist=1.0 --min_road_width=6.0 --typical_width=30.0 --max_height=2.0 --max_width=60.0 --max_increment=0.05 --spillout_slope=4.0 --remove_embankments=true",
             short_exe, name
         )
         .replace("*", &sep);
@@ -239,145 +239,22 @@ impl WhiteboxTool for EmbankmentMapping {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut roads_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let roads_file = parsed.get_string("road_vec").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         // parameters
-        let mut search_dist = 2.5f64;
-        let mut min_road_width = 6.0;
-        let mut typical_width = 30.0;
-        let mut max_height = 2.0;
-        let mut max_width = 60.0;
-        let mut max_increment = 0.05;
-        let mut spillout_slope = 4.0;
-        let mut remove_embankments = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-road_vec" {
-                roads_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-search_dist" {
-                search_dist = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-min_road_width" {
-                min_road_width = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-typical_width" {
-                typical_width = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_height" {
-                max_height = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_width" {
-                max_width = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_increment" {
-                max_increment = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-spillout_slope" {
-                spillout_slope = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-remove_embankments" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    remove_embankments = true;
-                }
-            }
-        }
+        let search_dist = parsed.get_f64("search_dist").unwrap_or(2.5f64);
+        let mut min_road_width = parsed.get_f64("min_road_width").unwrap_or(6.0);
+        let mut typical_width = parsed.get_f64("typical_width").unwrap_or(30.0);
+        let max_height = parsed.get_f64("max_height").unwrap_or(2.0);
+        let mut max_width = parsed.get_f64("max_width").unwrap_or(60.0);
+        let max_increment = parsed.get_f64("max_increment").unwrap_or(0.05);
+        let spillout_slope = parsed.get_f64("spillout_slope").unwrap_or(4.0);
+        let remove_embankments = parsed.get_bool("remove_embankments");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -390,8 +267,6 @@ impl WhiteboxTool for EmbankmentMapping {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -429,12 +304,8 @@ impl WhiteboxTool for EmbankmentMapping {
         typical_width /= 2.0;
         max_width /= 2.0;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -596,15 +467,11 @@ impl WhiteboxTool for EmbankmentMapping {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * (record_num + 1) as f64 / num_records as f64) as usize;
                 if progress != old_progress {
-                    println!(
-                        "Rasterizing roads {} of {}: {}%",
-                        record_num + 1,
-                        num_records,
-                        progress
-                    );
+                    reporter.progress(&format!("Rasterizing roads {} of {}", record_num + 1, num_records), progress);
                     old_progress = progress;
                 }
             }
@@ -686,6 +553,7 @@ impl WhiteboxTool for EmbankmentMapping {
         }
 
         for row in 0..rows {
+            reporter.check_cancelled()?;
             for col in 0..columns {
                 if roads.get_value(row, col) > 0 {
                     maxval = dem.get_value(row, col);
@@ -722,7 +590,7 @@ impl WhiteboxTool for EmbankmentMapping {
             }
             progress = (100.0 * row as f64 / (rows - 1) as f64) as usize;
             if progress != old_progress && verbose {
-                println!("Progress: {}%", progress);
+                reporter.progress("Progress", progress);
                 old_progress = progress;
             }
         }
@@ -738,7 +606,7 @@ impl WhiteboxTool for EmbankmentMapping {
 
             for n in 0..8 {
                 zn = dem.get_value(r + dy[n], c + dx[n]);
-                if zn != nodata && distance.get_value(r + dy[n], c + dx[n]) < 0.0 {
+                if !is_nodata(zn, nodata) && distance.get_value(r + dy[n], c + dx[n]) < 0.0 {
                     dist = cell.distance + dist_array[n];
                     if dist < max_width {
                         distance.set_value(r + dy[n], c + dx[n], dist);
@@ -764,11 +632,12 @@ impl WhiteboxTool for EmbankmentMapping {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 num_mapped_cells += 1f64;
                 progress = (100.0 * num_mapped_cells / (num_cells as f64)) as usize;
                 if progress != old_progress {
-                    println!("Percent of total cells parameterized: {}%", progress);
+                    reporter.progress("Percent of total cells parameterized", progress);
                     old_progress = progress;
                 }
             }
@@ -776,6 +645,7 @@ impl WhiteboxTool for EmbankmentMapping {
 
         num_mapped_cells = 0.0;
         while pqueue.len() != 0 {
+            reporter.check_cancelled()?;
             cell = pqueue.pop().expect("Error during pop operation.");
             r = cell.row;
             c = cell.col;
@@ -784,7 +654,7 @@ impl WhiteboxTool for EmbankmentMapping {
 
             for n in 0..8 {
                 zn = dem.get_value(r + dy[n], c + dx[n]);
-                if zn != nodata && output.get_value(r + dy[n], c + dx[n]) == nodata {
+                if !is_nodata(zn, nodata) && is_nodata(output.get_value(r + dy[n], c + dx[n]), nodata) {
                     dist = distance.get_value(r + dy[n], c + dx[n]);
                     if dist >= 0.0 {
                         if dist <= min_road_width {
@@ -842,7 +712,7 @@ impl WhiteboxTool for EmbankmentMapping {
             num_mapped_cells += 1f64;
             progress = (100.0 * num_mapped_cells / total_cells_in_buffer) as usize;
             if progress != old_progress && verbose {
-                println!("Progress: {}%", progress);
+                reporter.progress("Progress", progress);
                 old_progress = progress;
             }
         }
@@ -873,9 +743,9 @@ impl WhiteboxTool for EmbankmentMapping {
                         for i in 0..8 {
                             row_n = row + dy[i];
                             col_n = col + dx[i];
-                            if (output.get_value(row_n, col_n) == nodata
+                            if (is_nodata(output.get_value(row_n, col_n), nodata)
                                 || output.get_value(row_n, col_n) == 0f64)
-                                && dem.get_value(row_n, col_n) != nodata
+                                && !is_nodata(dem.get_value(row_n, col_n), nodata)
                             {
                                 y = dem.get_y_from_row(row_n);
                                 x = dem.get_x_from_column(col_n);
@@ -884,10 +754,11 @@ impl WhiteboxTool for EmbankmentMapping {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f32 * row as f32 / (rows - 1) as f32) as usize;
                     if progress != old_progress {
-                        println!("Finding edge cells: {}%", progress);
+                        reporter.progress("Finding edge cells", progress);
                         old_progress = progress;
                     }
                 }
@@ -930,10 +801,11 @@ impl WhiteboxTool for EmbankmentMapping {
                         out_dem.set_value(row, col, dem.get_value(row, col));
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f32 * row as f32 / (rows - 1) as f32) as usize;
                     if progress != old_progress {
-                        println!("Interpolating data holes: {}%", progress);
+                        reporter.progress("Interpolating data holes", progress);
                         old_progress = progress;
                     }
                 }
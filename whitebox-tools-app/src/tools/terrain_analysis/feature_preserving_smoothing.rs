@@ -9,15 +9,14 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::ops::AddAssign;
 use std::ops::SubAssign;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 use std::{f32, f64};
 
 /// This tool implements a highly modified form of the DEM de-noising algorithm described
@@ -87,7 +86,7 @@ impl FeaturePreservingSmoothing {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -117,7 +116,7 @@ impl FeaturePreservingSmoothing {
             flags: vec!["--norm_diff".to_owned()],
             description: "Maximum difference in normal vectors, in degrees.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("15.0".to_owned()),
+            default_value: Some("8.0".to_owned()),
             optional: true,
         });
 
@@ -135,7 +134,7 @@ impl FeaturePreservingSmoothing {
             flags: vec!["--max_diff".to_owned()],
             description: "Maximum allowable absolute elevation change (optional).".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("0.5".to_owned()),
+            default_value: None,
             optional: true,
         });
 
@@ -218,105 +217,16 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size = 11usize;
-        let mut max_norm_diff = 8f32;
-        let mut num_iter = 3;
-        let mut z_factor = -1f32;
-        let mut max_z_diff = f32::INFINITY;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-filter" {
-                filter_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-norm_diff" {
-                max_norm_diff = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-num_iter" {
-                num_iter = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val)) as usize
-                };
-            } else if flag_val == "-zfactor" {
-                z_factor = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_diff" {
-                max_z_diff = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(11usize);
+        let mut max_norm_diff = parsed.get_f64("norm_diff").map(|v| v as f32).unwrap_or(8f32);
+        let mut num_iter = parsed.get_usize("num_iter")?.unwrap_or(3);
+        let mut z_factor = parsed.get_f64("zfactor").map(|v| v as f32).unwrap_or(-1f32);
+        let max_z_diff = parsed.get_f64("max_diff").map(|v| v as f32).unwrap_or(f32::INFINITY);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -340,17 +250,11 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
         }
         let threshold = max_norm_diff.to_radians().cos();
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -396,17 +300,13 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
         ///////////////////////////////
         // Create the normal vectors //
         ///////////////////////////////
-        let mut num_procs = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let mut z: f32;
@@ -417,10 +317,10 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
                     let mut values = [0f32; 9];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z as f64, nodata as f64) {
                             for i in 0..8 {
                                 zn = input.get_value(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata(zn as f64, nodata as f64) {
                                     values[i] = (zn * z_factor) as f32;
                                 } else {
                                     values[i] = (z * z_factor) as f32;
@@ -443,7 +343,9 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
                             data[col as usize] = Normal { a: a, b: b };
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -454,10 +356,11 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
             let data = rx.recv().expect("Error receiving data from thread.");
             nv.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating normal vectors: {}%", progress);
+                    reporter.progress("Calculating normal vectors", progress);
                     old_progress = progress;
                 }
             }
@@ -483,7 +386,7 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
             let input = input.clone();
             let nv = nv.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let num_pixels_in_filter = filter_size * filter_size;
                 let mut dx = vec![0isize; num_pixels_in_filter];
                 let mut dy = vec![0isize; num_pixels_in_filter];
@@ -509,14 +412,14 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
                     let mut data = vec![Normal { a: 0f32, b: 0f32 }; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z as f64, nodata as f64) {
                             sum_w = 0f32;
                             a = 0f32;
                             b = 0f32;
                             for n in 0..num_pixels_in_filter {
                                 xn = col + dx[n];
                                 yn = row + dy[n];
-                                if input.get_value(yn, xn) != nodata {
+                                if !is_nodata(input.get_value(yn, xn) as f64, nodata as f64) {
                                     diff =
                                         nv.get_value(row, col).angle_between(nv.get_value(yn, xn));
                                     if diff > threshold32 {
@@ -534,7 +437,9 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
                             data[col as usize] = Normal { a: a, b: b };
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -544,10 +449,11 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
             let data = rx.recv().expect("Error receiving data from thread.");
             nv_smooth.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Smoothing normal vectors: {}%", progress);
+                    reporter.progress("Smoothing normal vectors", progress);
                     old_progress = progress;
                 }
             }
@@ -582,6 +488,7 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
         // configs.nodata = nodata as f64;
         let mut output: Array2D<f32> = Array2D::new(rows, columns, nodata, nodata)?; //Raster::initialize_using_config(&output_file, &configs);
         for row in 0..rows {
+            reporter.check_cancelled()?;
             for col in 0..columns {
                 output.set_value(row, col, input.get_value(row, col));
             }
@@ -599,14 +506,14 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
 
             for row in 0..rows {
                 for col in 0..columns {
-                    if input.get_value(row, col) != nodata {
+                    if !is_nodata(input.get_value(row, col) as f64, nodata as f64) {
                         sum_w = 0f32;
                         z = 0f32;
                         for n in 0..8 {
                             xn = col + dx[n];
                             yn = row + dy[n];
                             zn = output.get_value(yn, xn);
-                            if zn != nodata {
+                            if !is_nodata(zn as f64, nodata as f64) {
                                 diff = nv_smooth
                                     .get_value(row, col)
                                     .angle_between(nv_smooth.get_value(yn, xn));
@@ -633,15 +540,11 @@ impl WhiteboxTool for FeaturePreservingSmoothing {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Updating DEM elevations (Loop {} of {}): {}%",
-                            loop_num + 1,
-                            num_iter,
-                            progress
-                        );
+                        reporter.progress(&format!("Updating DEM elevations (Loop {} of {})", loop_num + 1, num_iter), progress);
                         old_progress = progress;
                     }
                 }
@@ -6,17 +6,16 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::structures::{Array2D, DistanceMetric, FixedRadiusSearch2D};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to fill in small gaps in a raster or digital elevation model (DEM). The gaps,
 /// or holes, must have recognized NoData values. If gaps do not currently have this characteristic, use
@@ -97,7 +96,7 @@ impl FillMissingData {
                 "Optional flag indicating whether to exclude NoData cells in edge regions."
                     .to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_string()),
+            default_value: Some("false".to_string()),
             optional: true,
         });
 
@@ -169,70 +168,14 @@ impl WhiteboxTool for FillMissingData {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size = 11usize;
-        let mut weight = 2.0f64;
-        let mut exclude_edge_nodata = false;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-filter" {
-                if keyval {
-                    filter_size = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-weight" {
-                weight = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-no_edges" {
-                exclude_edge_nodata = true;
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("input").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(11usize);
+        let weight = parsed.get_f64("weight").unwrap_or(2.0f64);
+        let exclude_edge_nodata = parsed.get_bool("no_edges");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -245,8 +188,6 @@ impl WhiteboxTool for FillMissingData {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         // The filter dimensions must be odd numbers such that there is a middle pixel
         if (filter_size as f64 / 2f64).floor() == (filter_size as f64 / 2f64) {
             filter_size += 1;
@@ -257,12 +198,8 @@ impl WhiteboxTool for FillMissingData {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -288,24 +225,25 @@ impl WhiteboxTool for FillMissingData {
             // Scan the raster edges, placing NoData values in a stack
             let mut stack = vec![]; // Vec::with_capacity(((rows + columns) * 5) as usize);
             for row in 0..rows {
-                if input.get_value(row, 0) == nodata {
+                reporter.check_cancelled()?;
+                if is_nodata(input.get_value(row, 0), nodata) {
                     stack.push((row, 0));
                     edge_connected_nodata.set_value(row, 0, 1);
                 }
 
-                if input.get_value(row, columns - 1) == nodata {
+                if is_nodata(input.get_value(row, columns - 1), nodata) {
                     stack.push((row, columns - 1));
                     edge_connected_nodata.set_value(row, columns - 1, 1);
                 }
             }
 
             for col in 0..columns {
-                if input.get_value(0, col) == nodata {
+                if is_nodata(input.get_value(0, col), nodata) {
                     stack.push((0, col));
                     edge_connected_nodata.set_value(0, col, 1);
                 }
 
-                if input.get_value(rows - 1, col) == nodata {
+                if is_nodata(input.get_value(rows - 1, col), nodata) {
                     stack.push((rows - 1, col));
                     edge_connected_nodata.set_value(rows - 1, col, 1);
                 }
@@ -315,19 +253,20 @@ impl WhiteboxTool for FillMissingData {
             let mut num_cells_popped = 0;
             while let Some(cell) = stack.pop() {
                 for n in 0..8 {
-                    if input.get_value(cell.0 + dy[n], cell.1 + dx[n]) == nodata
+                    if is_nodata(input.get_value(cell.0 + dy[n], cell.1 + dx[n]), nodata)
                         && edge_connected_nodata.get_value(cell.0 + dy[n], cell.1 + dx[n]) == 0
                     {
                         stack.push((cell.0 + dy[n], cell.1 + dx[n]));
                         edge_connected_nodata.set_value(cell.0 + dy[n], cell.1 + dx[n], 1);
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     num_cells_popped += 1;
                     progress = (100.0_f64 * num_cells_popped as f64 / (rows * columns - 1) as f64)
                         as usize;
                     if progress != old_progress {
-                        println!("Finding edge-connected NoData cells: {}%", progress);
+                        reporter.progress("Finding edge-connected NoData cells", progress);
                         old_progress = progress;
                     }
                 }
@@ -345,11 +284,11 @@ impl WhiteboxTool for FillMissingData {
         }
         for row in 0..rows {
             for col in 0..columns {
-                if input.get_value(row, col) != nodata {
+                if !is_nodata(input.get_value(row, col), nodata) {
                     for i in 0..8 {
                         row_n = row + dy[i];
                         col_n = col + dx[i];
-                        if input.get_value(row_n, col_n) == nodata
+                        if is_nodata(input.get_value(row_n, col_n), nodata)
                             && edge_connected_nodata.get_value(row_n, col_n) == 0
                         {
                             frs.insert(col as f64, row as f64, input[(row, col)]);
@@ -358,10 +297,11 @@ impl WhiteboxTool for FillMissingData {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Interpolating data holes: {}%", progress);
+                    reporter.progress("Interpolating data holes", progress);
                     old_progress = progress;
                 }
             }
@@ -370,19 +310,15 @@ impl WhiteboxTool for FillMissingData {
         let input = Arc::new(input);
         let edge_connected_nodata = Arc::new(edge_connected_nodata);
         let frs = Arc::new(frs);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let frs = frs.clone();
             let tx1 = tx.clone();
             let edge_connected_nodata = edge_connected_nodata.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let nodata = input.configs.nodata;
                 let columns = input.configs.columns as isize;
                 let mut z: f64;
@@ -393,7 +329,7 @@ impl WhiteboxTool for FillMissingData {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
-                                if input.get_value(row, col) == nodata
+                                if is_nodata(input.get_value(row, col), nodata)
                                     && edge_connected_nodata.get_value(row, col) == 0
                                 {
                                     sum_weights = 0f64;
@@ -420,14 +356,16 @@ impl WhiteboxTool for FillMissingData {
                                     data[col as usize] = input[(row, col)];
                                 }
                             }
-                            tx1.send((row, data)).unwrap();
+                            if tx1.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     }
                     _ => {
                         for row in (0..rows).filter(|r| r % num_procs == tid) {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
-                                if input.get_value(row, col) == nodata
+                                if is_nodata(input.get_value(row, col), nodata)
                                     && edge_connected_nodata.get_value(row, col) == 0
                                 {
                                     sum_weights = 0f64;
@@ -454,7 +392,9 @@ impl WhiteboxTool for FillMissingData {
                                     data[col as usize] = input[(row, col)];
                                 }
                             }
-                            tx1.send((row, data)).unwrap();
+                            if tx1.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     }
                 }
@@ -494,10 +434,11 @@ impl WhiteboxTool for FillMissingData {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    reporter.progress("Performing analysis", progress);
                     old_progress = progress;
                 }
             }
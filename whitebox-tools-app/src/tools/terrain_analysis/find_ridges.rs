@@ -6,16 +6,15 @@ Last Modified: 18/10/2019
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to identify ridge cells in a digital elevation model (DEM). Ridge cells are those that
 /// have lower neighbours either to the north and south or the east and west. Line thinning can optionally be used
@@ -59,7 +58,7 @@ impl FindRidges {
             flags: vec!["--line_thin".to_owned()], 
             description: "Optional flag indicating whether post-processing line-thinning should be performed.".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true
         });
 
@@ -132,49 +131,12 @@ impl WhiteboxTool for FindRidges {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut line_thin = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            if vec[0].to_lowercase() == "-i"
-                || vec[0].to_lowercase() == "-dem"
-                || vec[0].to_lowercase() == "--dem"
-            {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-o" || vec[0].to_lowercase() == "--output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-line_thin"
-                || vec[0].to_lowercase() == "--line_thin"
-            {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    line_thin = true;
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let line_thin = parsed.get_bool("line_thin");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -187,14 +149,8 @@ impl WhiteboxTool for FindRidges {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let input = Arc::new(Raster::new(&input_file, "r")?);
 
@@ -211,17 +167,13 @@ impl WhiteboxTool for FindRidges {
         output.reinitialize_values(0f64);
 
         // This one can be performed conccurently.
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = vec![0, 0, -1, 1];
                 let dy = vec![-1, 1, 0, 0];
                 let mut z: f64;
@@ -231,21 +183,23 @@ impl WhiteboxTool for FindRidges {
                     let mut data = vec![nodata; columns as usize];
                     for col in 0..columns {
                         z = input[(row, col)];
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             zn1 = input.get_value(row + dy[0], col + dx[0]);
                             zn2 = input.get_value(row + dy[1], col + dx[1]);
-                            if zn1 != nodata && zn2 != nodata && zn1 < z && zn2 < z {
+                            if !is_nodata(zn1, nodata) && !is_nodata(zn2, nodata) && zn1 < z && zn2 < z {
                                 data[col as usize] = 1f64;
                             } else {
                                 zn1 = input.get_value(row + dy[2], col + dx[2]);
                                 zn2 = input.get_value(row + dy[3], col + dx[3]);
-                                if zn1 != nodata && zn2 != nodata && zn1 < z && zn2 < z {
+                                if !is_nodata(zn1, nodata) && !is_nodata(zn2, nodata) && zn1 < z && zn2 < z {
                                     data[col as usize] = 1f64;
                                 }
                             }
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -253,10 +207,11 @@ impl WhiteboxTool for FindRidges {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as i32;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress as usize);
                     old_progress = progress;
                 }
             }
@@ -302,7 +257,7 @@ impl WhiteboxTool for FindRidges {
                     for row in 0..rows {
                         for col in 0..columns {
                             z = output[(row, col)];
-                            if z > 0.0 && z != nodata {
+                            if z > 0.0 && !is_nodata(z, nodata) {
                                 // fill the neighbours array
                                 for i in 0..8 {
                                     neighbours[i] = output[(row + dy[i], col + dx[i])];
@@ -322,10 +277,11 @@ impl WhiteboxTool for FindRidges {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * a as f64 / 7.0) as i32;
                         if progress != old_progress {
-                            println!("Loop Number {}: {}%", loop_num, progress);
+                            reporter.progress(&format!("Loop Number {}", loop_num), progress as usize);
                             old_progress = progress;
                         }
                     }
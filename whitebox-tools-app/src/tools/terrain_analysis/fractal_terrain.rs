@@ -0,0 +1,202 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::synthetic_terrain::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool creates a synthetic fractal terrain (`--output`) using the diamond-square (midpoint
+/// displacement) algorithm. The roughness of the surface is set by its Hurst exponent
+/// (`--roughness`, 0 < H <= 1), by which the random displacements are scaled at each level of
+/// subdivision; values near 1 give smooth, rolling surfaces and lower values rugged ones. The
+/// elevations are scaled to range from zero to the relief (`--relief`).
+///
+/// The output grid is that of a base raster (`--base`), if one is given, and otherwise has the
+/// given numbers of rows (`--rows`) and columns (`--columns`) and cell size (`--cell_size`), with
+/// its lower-left corner at the origin. The surface is determined by its parameters and the random
+/// seed (`--seed`); the same seed gives the same surface on every platform, such that synthetic
+/// terrains can serve as reproducible test data. Diamond-square surfaces are generated on a square
+/// grid of 2^n + 1 cells that covers the output, and their statistics are not stationary, with
+/// visible creases along the lines of the first subdivisions; `PerlinNoiseTerrain` is preferable
+/// where this matters.
+///
+/// # See Also
+/// `GaussianHills`, `PerlinNoiseTerrain`, `SyntheticValley`, `TurningBandsSimulation`
+pub struct FractalTerrain {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl FractalTerrain {
+    pub fn new() -> FractalTerrain {
+        // public constructor
+        let name = "FractalTerrain".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Creates a seeded synthetic fractal terrain using the diamond-square algorithm."
+                .to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.extend(grid_parameters());
+
+        parameters.push(ToolParameter {
+            name: "Roughness (Hurst Exponent)".to_owned(),
+            flags: vec!["--roughness".to_owned()],
+            description: "Hurst exponent (0 < H <= 1); lower values give rougher surfaces.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.8".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Relief".to_owned(),
+            flags: vec!["--relief".to_owned()],
+            description: "Range of the output elevations.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("100.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(seed_parameter());
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -o=fractal.tif --rows=1025 --columns=1025 --cell_size=5.0 --roughness=0.7 --relief=250.0 --seed=7", short_exe, name).replace("*", &sep);
+
+        FractalTerrain {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for FractalTerrain {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let output_file = resolve_path(&parsed.get_string("output").unwrap_or_default(), working_directory);
+        let roughness = parsed.get_f64("roughness").unwrap_or(0.8);
+        let relief = parsed.get_f64("relief").unwrap_or(100.0);
+        let seed = parsed.get_i64("seed").unwrap_or(42) as u64;
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if !(roughness > 0.0 && roughness <= 1.0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --roughness parameter must be greater than 0 and no greater than 1.",
+            ));
+        }
+
+        let configs = output_configs(&parsed, working_directory)?;
+
+        let start = Instant::now();
+
+        if verbose {
+            println!("Generating surface...");
+        }
+        let mut surface = diamond_square(configs.rows, configs.columns, roughness, seed);
+        reporter.check_cancelled()?;
+        rescale(&mut surface, relief);
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let metadata = vec![
+            format!("Created by whitebox_tools\' {} tool", self.get_tool_name()),
+            format!("Roughness: {}", roughness),
+            format!("Relief: {}", relief),
+            format!("Seed: {}", seed),
+            format!("Elapsed Time (excluding I/O): {}", elapsed_time),
+        ];
+        write_surface(&output_file, &configs, surface, metadata, verbose)?;
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
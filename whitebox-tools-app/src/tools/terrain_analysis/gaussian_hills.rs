@@ -0,0 +1,234 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::synthetic_terrain::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool creates a synthetic terrain (`--output`) of Gaussian hills, i.e. the sum of a number
+/// (`--num_hills`) of bell-shaped hills, each of which has the form h exp(-d^2 / 2s^2), where d is
+/// the distance from the hill's centre. The hills are placed at random within the grid, with heights
+/// (h) between half of and the full relief (`--relief`) and radii (s) between one-half and
+/// one-and-a-half times the radius (`--radius`, in map units), which defaults to one-tenth of the
+/// smaller dimension of the grid. Because the surface is analytic, its slope, curvature, and
+/// drainage are known exactly, which makes Gaussian hills useful test surfaces for terrain analysis
+/// tools; a single hill is centred on the grid when `--num_hills=1`.
+///
+/// The output grid is that of a base raster (`--base`), if one is given, and otherwise has the
+/// given numbers of rows (`--rows`) and columns (`--columns`) and cell size (`--cell_size`), with
+/// its lower-left corner at the origin. The surface is determined by its parameters and the random
+/// seed (`--seed`); the same seed gives the same surface on every platform.
+///
+/// # See Also
+/// `FractalTerrain`, `PerlinNoiseTerrain`, `SyntheticValley`
+pub struct GaussianHills {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl GaussianHills {
+    pub fn new() -> GaussianHills {
+        // public constructor
+        let name = "GaussianHills".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Creates a seeded synthetic terrain of randomly placed Gaussian hills.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.extend(grid_parameters());
+
+        parameters.push(ToolParameter {
+            name: "Number of Hills".to_owned(),
+            flags: vec!["--num_hills".to_owned()],
+            description: "Number of hills.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Relief".to_owned(),
+            flags: vec!["--relief".to_owned()],
+            description: "Height of the highest hills.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("100.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Hill Radius".to_owned(),
+            flags: vec!["--radius".to_owned()],
+            description: "Typical radius (standard deviation) of the hills, in map units. Defaults to one-tenth of the smaller grid dimension.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(seed_parameter());
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -o=hills.tif --rows=500 --columns=800 --cell_size=2.0 --num_hills=25 --relief=40.0 --radius=60.0 --seed=3", short_exe, name).replace("*", &sep);
+
+        GaussianHills {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for GaussianHills {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let output_file = resolve_path(&parsed.get_string("output").unwrap_or_default(), working_directory);
+        let num_hills = parsed.get_usize("num_hills")?.unwrap_or(10);
+        let relief = parsed.get_f64("relief").unwrap_or(100.0);
+        let radius = parsed.get_f64("radius");
+        let seed = parsed.get_i64("seed").unwrap_or(42) as u64;
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if num_hills == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --num_hills parameter must be at least 1.",
+            ));
+        }
+
+        let configs = output_configs(&parsed, working_directory)?;
+        let radius = radius.unwrap_or(0.1 * (configs.north - configs.south).min(configs.east - configs.west));
+        if radius <= 0.0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --radius parameter must be greater than zero.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        let hills = if num_hills == 1 {
+            vec![GaussianHill {
+                x: (configs.west + configs.east) / 2.0,
+                y: (configs.south + configs.north) / 2.0,
+                height: relief,
+                sigma: radius,
+            }]
+        } else {
+            gaussian_hills(&configs, num_hills, relief, radius, seed)
+        };
+        let (west, north) = (configs.west, configs.north);
+        let (res_x, res_y) = (configs.resolution_x, configs.resolution_y);
+        let surface = compute_surface(
+            configs.rows as isize,
+            configs.columns as isize,
+            move |row, col| {
+                let x = west + (col as f64 + 0.5) * res_x;
+                let y = north - (row as f64 + 0.5) * res_y;
+                hills.iter().map(|h| h.elevation(x, y)).sum()
+            },
+            verbose,
+            reporter,
+        )?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let metadata = vec![
+            format!("Created by whitebox_tools\' {} tool", self.get_tool_name()),
+            format!("Number of hills: {}", num_hills),
+            format!("Relief: {}", relief),
+            format!("Radius: {}", radius),
+            format!("Seed: {}", seed),
+            format!("Elapsed Time (excluding I/O): {}", elapsed_time),
+        ];
+        write_surface(&output_file, &configs, surface, metadata, verbose)?;
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
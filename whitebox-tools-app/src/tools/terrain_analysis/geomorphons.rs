@@ -9,14 +9,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::path;
 use std::f64;
 use std::sync::Arc;
 use std::sync::mpsc;
-use std::thread;
-use std::io::{Error, ErrorKind};
+use whitebox_common::utils::spawn;
+use std::io::Error;
 use nalgebra::{Vector3, Matrix3};
 //use std::collections::HashSet;
 
@@ -84,7 +83,7 @@ impl Geomorphons {
         let mut parameters = vec![];
         parameters.push(ToolParameter{
             name: "Input DEM file.".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -105,7 +104,7 @@ impl Geomorphons {
             flags: vec!["--search".to_owned()],
             description: "Look up distance (in cells).".to_owned(),
             parameter_type: ParameterType::Integer,
-            default_value: Some("50".to_owned()),
+            default_value: Some("1".to_owned()),
             optional: false
         });
 
@@ -114,7 +113,7 @@ impl Geomorphons {
             flags: vec!["--threshold".to_owned()],
             description: "Flatness threshold for the classification function (in degrees).".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("0.0".to_owned()),
+            default_value: Some("1.0".to_owned()),
             optional: false
         });
 
@@ -141,7 +140,7 @@ impl Geomorphons {
             flags: vec!["-f".to_owned(), "--forms".to_owned()],
             description: "Classify geomorphons into 10 common land morphologies, else output ternary pattern.".to_owned(),
             parameter_type: ParameterType::Boolean,
-            default_value: Some("true".to_owned()),
+            default_value: Some("false".to_owned()),
             optional: true
         });
 
@@ -210,81 +209,31 @@ impl WhiteboxTool for Geomorphons {
         self.toolbox.clone()
     }
 
-    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
+    fn run<'a>(&self, args: Vec<String>, working_directory: &'a str, verbose: bool, reporter: &dyn ProgressReporter) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut search_radius_cells: usize = 1;
-        let mut flat_thresh: f64 = 1f64;
+        let flat_thresh: f64 = parsed.get_f64("threshold").unwrap_or(1f64);
         let mut flat_dist_cells: usize = 0;
         let mut skip_dist_cells: usize = 0;
-        let mut forms: bool = false;
-        let mut use_residuals = false;
+        let forms: bool = parsed.get_bool("forms");
+        let use_residuals = parsed.get_bool("residuals");
 
-        if args.len() == 0 {
-            return Err(Error::new(ErrorKind::InvalidInput,
-                                "Tool run with no parameters."));
+        if let Some(value) = parsed.get_string("search") {
+            search_radius_cells = value.to_string().parse::<isize>()
+                .expect("Error parsing --search")
+                .abs() as usize;
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-dem" || flag_val == "-input" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i+1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i+1].to_string()
-                };
-            } else if flag_val == "-search" {
-                search_radius_cells = if keyval {
-                    vec[1].to_string().parse::<isize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        .abs() as usize
-                } else {
-                    args[i+1].to_string().parse::<isize>().unwrap().abs() as usize
-                };
-            } else if flag_val == "-threshold" {
-                flat_thresh = if keyval {
-                    vec[1].to_string().parse::<f64>().expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i+1].to_string().parse::<f64>().unwrap()
-                };
-            } else if flag_val == "-fdist" {
-                flat_dist_cells = if keyval {
-                    vec[1].to_string().parse::<isize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        .abs() as usize
-                } else {
-                    args[i+1].to_string().parse::<isize>().unwrap().abs() as usize
-                };
-            } else if flag_val == "-skip" {
-                skip_dist_cells = if keyval {
-                    vec[1].to_string().parse::<isize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        .abs() as usize
-                } else {
-                    args[i+1].to_string().parse::<isize>().unwrap().abs() as usize
-                };
-            } else if flag_val == "-f" || flag_val == "-forms" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    forms = true;
-                }
-            } else if flag_val == "-residuals" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    use_residuals = true;
-                }
-            }
+        if let Some(value) = parsed.get_string("fdist") {
+            flat_dist_cells = value.to_string().parse::<isize>()
+                .expect("Error parsing --fdist")
+                .abs() as usize;
+        }
+        if let Some(value) = parsed.get_string("skip") {
+            skip_dist_cells = value.to_string().parse::<isize>()
+                .expect("Error parsing --skip")
+                .abs() as usize;
         }
 
         if verbose {
@@ -298,17 +247,11 @@ impl WhiteboxTool for Geomorphons {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose { println!("Reading data...") };
 
@@ -341,16 +284,12 @@ impl WhiteboxTool for Geomorphons {
             println!("Generating global ternary codes...");
         }
         let max_codes: usize = 6561; // = 3^8 for 8-bit ternary
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut power, mut k): (i32, i32);
                 let (mut value, mut data): (i32, i32);
                 let (mut code, mut rev_code): (i32, i32);
@@ -401,7 +340,9 @@ impl WhiteboxTool for Geomorphons {
                     if code < rev_code {
                         data = code;
                     } else { data = rev_code; }
-                    tx.send((val, data)).unwrap();
+                    if tx.send((val, data)).is_err() {
+                        return;
+                    }
                 }
             });
         };
@@ -421,10 +362,12 @@ impl WhiteboxTool for Geomorphons {
         if use_residuals {
             if verbose { println!("Calculating residuals..."); }
             let (tx, rx) = mpsc::channel();
+            let token = reporter.cancellation_token();
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                let token = token.clone();
+                spawn(move || {
                     let mut sum_y = 0f64;
                     let mut sum_xr_y = 0f64;
                     let mut sum_xc_y = 0f64;
@@ -437,11 +380,14 @@ impl WhiteboxTool for Geomorphons {
                     let mut z: f64;
                     let (mut r, mut c): (f64, f64);
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        if token.is_cancelled() {
+                            break;
+                        }
                         r = row as f64;
                         for col in 0..columns {
                             c = col as f64;
                             z = input.get_value(row, col);
-                            if z != nodata  {
+                            if !is_nodata(z, nodata)  {
                                 sum_y += z;
                                 sum_xr_y += r * z;
                                 sum_xc_y += c * z;
@@ -454,12 +400,13 @@ impl WhiteboxTool for Geomorphons {
                             }
                         }
                     }
-                    tx.send((sum_y, sum_xr_y, sum_xc_y, sum_xr, sum_xc,
-                                sum_xr_xr, sum_xc_xc, sum_xr_xc, n)).unwrap();
+                    if tx.send((sum_y, sum_xr_y, sum_xc_y, sum_xr, sum_xc,
+                                sum_xr_xr, sum_xc_xc, sum_xr_xc, n)).is_err() {
+                        return;
+                    }
                 });
             }
 
-
             let mut sum_y = 0f64;
             let mut sum_xr_y = 0f64;
             let mut sum_xc_y = 0f64;
@@ -483,6 +430,7 @@ impl WhiteboxTool for Geomorphons {
                 sum_xr_xc += xrxc;
                 n += num;
             }
+            reporter.check_cancelled()?;
             //          | n         sum_xr      sum_xc    |   | b0  |           | sum_y    |
             // X'X =    | sum_xr    sum_xr_xr   sum_xr_xc | . | b1r | =  X'Y =  | sum_xr_y |
             //          | sum_xc    sum_xr_xc   sum_xc_xc |   | b1c |           | sum_xc_y |
@@ -507,7 +455,7 @@ impl WhiteboxTool for Geomorphons {
             for tid in 0..num_procs {
                 let input = input.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let mut z: f64;
                     let (mut r, mut c): (f64, f64);
                     for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -516,12 +464,14 @@ impl WhiteboxTool for Geomorphons {
                         for col in 0..columns {
                             c = col as f64;
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 data[col as usize] = z -
                                     (b0 + (b1r * r) + (b1c * c)); // estimate
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -553,20 +503,15 @@ impl WhiteboxTool for Geomorphons {
                                     ];                                  // 0  = Error
 
         let gtc = Arc::new(gtc);
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let gtc = gtc.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let (mut z, mut z2, mut angle, mut distance): (f64, f64, f64, f64);
                 let (mut r, mut c, mut d): (isize, isize, isize);
                 let (mut x1, mut x2, mut xdif): (f64, f64, f64);
@@ -596,7 +541,7 @@ impl WhiteboxTool for Geomorphons {
                             y1 = input.get_y_from_row(row);
                             x1 = input.get_x_from_column(col);
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
 
                                 // scan profile in 8 compass directions
                                 'directions: for dir in 0..8 {
@@ -618,7 +563,7 @@ impl WhiteboxTool for Geomorphons {
                                     distance = (ydif*ydif + xdif*xdif).sqrt();
 
                                     while distance < search_length {
-                                        if z2 != nodata { // line-of-sight exists
+                                        if !is_nodata(z2, nodata) { // line-of-sight exists
                                             angle = (z2 - z).atan2(distance);
                                             if angle > zenith_angle { // get max angle
                                                 zenith_angle = angle;
@@ -682,7 +627,9 @@ impl WhiteboxTool for Geomorphons {
                             }
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -690,10 +637,11 @@ impl WhiteboxTool for Geomorphons {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
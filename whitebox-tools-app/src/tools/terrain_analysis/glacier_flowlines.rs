@@ -0,0 +1,492 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::glacier_outlines::{rasterize_outlines, read_outlines};
+use whitebox_raster::*;
+use whitebox_common::structures::{Array2D, Point2D};
+use crate::tools::*;
+use whitebox_vector::*;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::env;
+use std::f64;
+use std::io::Error;
+use std::path;
+
+/// This tool extracts glacier flowlines (centerlines) from glacier outline polygons (`--outlines`) and a digital
+/// elevation model (`--dem`). Flowlines are used to measure glacier length, to derive longitudinal profiles, and as
+/// the geometry of flowline glacier models. The method is similar to that of Kienholz et al. (2014). Within each
+/// glacier, a least-cost path tree is grown outward from the glacier terminus, i.e. the lowest grid cell within the
+/// outline. The cost of moving through a grid cell decreases with the cell's distance from the glacier margin,
+/// such that paths are drawn toward the centre of the ice, and paths that rise in the down-glacier direction are
+/// penalized, such that flowlines descend monotonically wherever possible.
+///
+/// Flowlines are traced down the least-cost path tree from a set of glacier heads. Head candidates are grid cells
+/// on the glacier margin lying in the upper half of the glacier's elevation range. The highest candidate becomes the
+/// head of the main flowline, which extends to the terminus, and further heads are selected, in order of decreasing
+/// elevation, from candidates lying at least the head spacing (`--head_spacing`, in map units) from all previously
+/// selected heads. Each tributary flowline ends where it joins a previously traced flowline. Tributaries shorter
+/// than a minimum length (`--min_length`) are discarded.
+///
+/// The output vector (`--output`) contains polylines, digitized from head to terminus or confluence, with the
+/// attributes GLAC_ID (the record number of the glacier outline, beginning at 1), MAIN (1 for the main flowline of
+/// each glacier, 0 for tributaries), LENGTH, Z_START, and Z_END. The DEM should be in a projected coordinate system.
+///
+/// # Reference
+/// Kienholz, C., Rich, J. L., Arendt, A. A., and Hock, R. (2014). A new method for deriving glacier centerlines
+/// applied to glaciers in Alaska and northwest Canada. *The Cryosphere*, 8(2), 503-519.
+///
+/// # See Also
+/// `GlacierHypsometry`, `LongestFlowpath`
+pub struct GlacierFlowlines {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl GlacierFlowlines {
+    pub fn new() -> GlacierFlowlines {
+        // public constructor
+        let name = "GlacierFlowlines".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Extracts glacier flowlines (centerlines) from glacier outlines and a DEM.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Glacier Outlines File".to_owned(),
+            flags: vec!["--outlines".to_owned()],
+            description: "Input vector glacier outline polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector flowlines file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Line,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Head Spacing".to_owned(),
+            flags: vec!["--head_spacing".to_owned()],
+            description: "Minimum distance between glacier heads, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("500.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Tributary Length".to_owned(),
+            flags: vec!["--min_length".to_owned()],
+            description: "Minimum length of tributary flowlines, in map units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --outlines=glaciers.shp -o=flowlines.shp --head_spacing=500.0 --min_length=250.0",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        GlacierFlowlines {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for GlacierFlowlines {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut outlines_file = parsed.get_string("outlines").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let head_spacing = parsed.get_f64("head_spacing").unwrap_or(500f64);
+        let min_length = parsed.get_f64("min_length").unwrap_or(0f64);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        outlines_file = resolve_path(&outlines_file, working_directory);
+        output_file = resolve_path(&output_file, working_directory);
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Raster::new(&dem_file, "r")?;
+        let outlines = read_outlines(&outlines_file)?;
+
+        let start = Instant::now();
+
+        let rows = dem.configs.rows as isize;
+        let columns = dem.configs.columns as isize;
+        let nodata = dem.configs.nodata;
+        let cell_size_x = dem.configs.resolution_x;
+        let cell_size_y = dem.configs.resolution_y;
+        let diag_cell_size = (cell_size_x * cell_size_x + cell_size_y * cell_size_y).sqrt();
+        let dx = [1, 1, 1, 0, -1, -1, -1, 0];
+        let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let grid_lengths = [
+            diag_cell_size,
+            cell_size_x,
+            diag_cell_size,
+            cell_size_y,
+            diag_cell_size,
+            cell_size_x,
+            diag_cell_size,
+            cell_size_y,
+        ];
+        let back_link = [4i8, 5, 6, 7, 0, 1, 2, 3];
+
+        if verbose {
+            println!("Rasterizing glacier outlines...")
+        };
+        let mut glacier_ids = rasterize_outlines(&outlines, &dem)?;
+        let mut glacier_cells: Vec<Vec<(isize, isize)>> = vec![vec![]; outlines.num_records];
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let id = glacier_ids.get_value(row, col);
+                if id >= 0 {
+                    if is_nodata(dem.get_value(row, col), nodata) {
+                        glacier_ids.set_value(row, col, -1);
+                    } else {
+                        glacier_cells[id as usize].push((row, col));
+                    }
+                }
+            }
+        }
+
+        // Distance from the glacier margin, using a two-pass chamfer transform.
+        let mut margin_dist: Array2D<f64> = Array2D::new(rows, columns, 0f64, -1f64)?;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let id = glacier_ids.get_value(row, col);
+                if id >= 0 {
+                    let is_margin = (0..8).any(|n| glacier_ids.get_value(row + dy[n], col + dx[n]) != id);
+                    margin_dist.set_value(row, col, if is_margin { 0f64 } else { f64::INFINITY });
+                }
+            }
+        }
+        for (row_range, n_range) in [
+            ((0..rows).collect::<Vec<isize>>(), [5usize, 6, 7, 0]),
+            ((0..rows).rev().collect::<Vec<isize>>(), [1usize, 2, 3, 4]),
+        ] {
+            for &row in &row_range {
+                let cols: Vec<isize> = if n_range[0] == 5 {
+                    (0..columns).collect()
+                } else {
+                    (0..columns).rev().collect()
+                };
+                for col in cols {
+                    let id = glacier_ids.get_value(row, col);
+                    if id < 0 {
+                        continue;
+                    }
+                    let mut d = margin_dist.get_value(row, col);
+                    for &n in &n_range {
+                        if glacier_ids.get_value(row + dy[n], col + dx[n]) == id {
+                            d = d.min(margin_dist.get_value(row + dy[n], col + dx[n]) + grid_lengths[n]);
+                        }
+                    }
+                    margin_dist.set_value(row, col, d);
+                }
+            }
+        }
+
+        let mut output = Shapefile::new(&output_file, ShapeType::PolyLine)?;
+        output.projection = dem.configs.coordinate_ref_system_wkt.clone();
+        output
+            .attributes
+            .add_field(&AttributeField::new("FID", FieldDataType::Int, 7u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("GLAC_ID", FieldDataType::Int, 7u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("MAIN", FieldDataType::Int, 1u8, 0u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("LENGTH", FieldDataType::Real, 12u8, 3u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("Z_START", FieldDataType::Real, 10u8, 3u8));
+        output
+            .attributes
+            .add_field(&AttributeField::new("Z_END", FieldDataType::Real, 10u8, 3u8));
+
+        let mut accum_cost: Array2D<f64> = Array2D::new(rows, columns, f64::INFINITY, -1f64)?;
+        let mut links: Array2D<i8> = Array2D::new(rows, columns, -1i8, -1i8)?;
+        let mut on_flowline: Array2D<u8> = Array2D::new(rows, columns, 0u8, 0u8)?;
+        let mut fid = 1i32;
+        for glacier in 0..outlines.num_records {
+            let cells = &glacier_cells[glacier];
+            if cells.len() < 3 {
+                continue;
+            }
+            let id = glacier as i32;
+            let (mut z_min, mut z_max) = (f64::INFINITY, f64::NEG_INFINITY);
+            let mut terminus = cells[0];
+            let mut d_max = 0f64;
+            for &(row, col) in cells {
+                let z = dem.get_value(row, col);
+                if z < z_min {
+                    z_min = z;
+                    terminus = (row, col);
+                }
+                z_max = z_max.max(z);
+                d_max = d_max.max(margin_dist.get_value(row, col));
+            }
+            d_max = d_max.max(f64::EPSILON);
+
+            // Grow the least-cost path tree outward from the terminus.
+            accum_cost.set_value(terminus.0, terminus.1, 0f64);
+            let mut queue = BinaryHeap::with_capacity(cells.len());
+            queue.push(GridCell {
+                row: terminus.0,
+                column: terminus.1,
+                priority: 0f64,
+            });
+            while let Some(cell) = queue.pop() {
+                let (row, col) = (cell.row, cell.column);
+                if cell.priority > accum_cost.get_value(row, col) {
+                    continue;
+                }
+                let z = dem.get_value(row, col);
+                for n in 0..8 {
+                    let (rn, cn) = (row + dy[n], col + dx[n]);
+                    if glacier_ids.get_value(rn, cn) != id {
+                        continue;
+                    }
+                    // Flow is from the neighbour toward this cell; penalize down-glacier rises.
+                    let rise = (z - dem.get_value(rn, cn)).max(0f64);
+                    let centrality = (d_max - margin_dist.get_value(rn, cn)) / d_max;
+                    let cost = grid_lengths[n]
+                        * (centrality * centrality + 0.1)
+                        * (1f64 + 10f64 * rise / grid_lengths[n]);
+                    let new_cost = cell.priority + cost;
+                    if new_cost < accum_cost.get_value(rn, cn) {
+                        accum_cost.set_value(rn, cn, new_cost);
+                        links.set_value(rn, cn, back_link[n]);
+                        queue.push(GridCell {
+                            row: rn,
+                            column: cn,
+                            priority: new_cost,
+                        });
+                    }
+                }
+            }
+
+            // Select the glacier heads.
+            let z_threshold = z_min + (z_max - z_min) / 2f64;
+            let mut candidates: Vec<(isize, isize, f64)> = cells
+                .iter()
+                .filter(|&&(row, col)| {
+                    margin_dist.get_value(row, col) == 0f64
+                        && dem.get_value(row, col) >= z_threshold
+                        && accum_cost.get_value(row, col) < f64::INFINITY
+                })
+                .map(|&(row, col)| (row, col, dem.get_value(row, col)))
+                .collect();
+            candidates.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(Ordering::Equal));
+            let mut heads: Vec<Point2D> = vec![];
+            on_flowline.set_value(terminus.0, terminus.1, 1u8);
+            for (row, col, z_start) in candidates {
+                let head = Point2D::new(dem.get_x_from_column(col), dem.get_y_from_row(row));
+                if heads.iter().any(|h| h.distance(&head) < head_spacing) {
+                    continue;
+                }
+                if on_flowline.get_value(row, col) == 1u8 {
+                    continue;
+                }
+                heads.push(head);
+
+                // Trace the flowline down the least-cost path tree.
+                let mut path = vec![(row, col)];
+                let (mut r, mut c) = (row, col);
+                let mut length = 0f64;
+                loop {
+                    let link = links.get_value(r, c);
+                    if link < 0 {
+                        break;
+                    }
+                    r += dy[link as usize];
+                    c += dx[link as usize];
+                    length += grid_lengths[link as usize];
+                    path.push((r, c));
+                    if on_flowline.get_value(r, c) == 1u8 {
+                        break;
+                    }
+                }
+                let is_main = heads.len() == 1;
+                if path.len() < 2 || (!is_main && length < min_length) {
+                    continue;
+                }
+                for &(r, c) in &path {
+                    on_flowline.set_value(r, c, 1u8);
+                }
+                let points: Vec<Point2D> = path
+                    .iter()
+                    .map(|&(r, c)| Point2D::new(dem.get_x_from_column(c), dem.get_y_from_row(r)))
+                    .collect();
+                let z_end = dem.get_value(path[path.len() - 1].0, path[path.len() - 1].1);
+                let mut sfg = ShapefileGeometry::new(ShapeType::PolyLine);
+                sfg.add_part(&points);
+                output.add_record(sfg);
+                output.attributes.add_record(
+                    vec![
+                        FieldData::Int(fid),
+                        FieldData::Int(glacier as i32 + 1),
+                        FieldData::Int(if is_main { 1 } else { 0 }),
+                        FieldData::Real(length),
+                        FieldData::Real(z_start),
+                        FieldData::Real(z_end),
+                    ],
+                    false,
+                );
+                fid += 1;
+            }
+
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * (glacier + 1) as f64 / outlines.num_records as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct GridCell {
+    row: isize,
+    column: isize,
+    priority: f64,
+}
+
+impl Eq for GridCell {}
+
+impl PartialOrd for GridCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.priority.partial_cmp(&self.priority)
+    }
+}
+
+impl Ord for GridCell {
+    fn cmp(&self, other: &GridCell) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+}
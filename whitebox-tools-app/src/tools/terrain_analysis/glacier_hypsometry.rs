@@ -0,0 +1,437 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::glacier_outlines::{rasterize_outlines, read_outlines};
+use whitebox_raster::*;
+use crate::tools::*;
+use whitebox_vector::*;
+use std::cmp::Ordering;
+use std::env;
+use std::f64;
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool calculates the hypsometry (area-elevation distribution) of each glacier in a set of glacier outline
+/// polygons (`--outlines`), using elevations from a digital elevation model (`--dem`), and estimates each glacier's
+/// equilibrium-line altitude (ELA) and accumulation-area ratio (AAR). The ELA is the elevation at which annual
+/// accumulation balances ablation, and the AAR is the proportion of the glacier's area lying above the ELA.
+///
+/// Two methods of ELA estimation are available (`--method`):
+///
+/// - **aar**: the accumulation-area ratio method. The ELA is the elevation above which the specified proportion
+///   (`--aar`, default 0.58) of the glacier's area lies.
+/// - **aabr**: the area-altitude balance ratio method (Osmaston, 2005). The mass-balance gradient is assumed to
+///   be linear on either side of the ELA, with the ablation gradient being a multiple, the balance ratio
+///   (`--balance_ratio`, default 1.75), of the accumulation gradient. The ELA is the elevation at which the
+///   area-weighted net balance of the glacier is zero. Elevations are grouped into bins of a user-specified
+///   size (`--bin_size`) for this calculation. A balance ratio of 1.0 is equivalent to the area-weighted mean
+///   elevation (AA) method.
+///
+/// The output vector (`--output`) is a copy of the outlines with the attributes GLAC_ID (the record number of the
+/// outline, beginning at 1), AREA, Z_MIN, Z_MAX, Z_MEAN, Z_MED, HI (the hypsometric integral), ELA, and AAR added.
+/// Optionally, the binned hypsometry of each glacier may be written to a CSV file (`--out_hypsometry`), with the
+/// glacier ID, the lower and upper bin elevations, the bin area, and the proportion of the glacier's area above
+/// the lower bin elevation. The DEM should be in a projected coordinate system.
+///
+/// # Reference
+/// Osmaston, H. (2005). Estimates of glacier equilibrium line altitudes by the Area×Altitude, the Area×Altitude
+/// Balance Ratio and the Area×Altitude Balance Index methods and their validation. *Quaternary International*,
+/// 138, 22-31.
+///
+/// # See Also
+/// `GlacierFlowlines`, `HypsometricAnalysis`
+pub struct GlacierHypsometry {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl GlacierHypsometry {
+    pub fn new() -> GlacierHypsometry {
+        // public constructor
+        let name = "GlacierHypsometry".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Calculates glacier hypsometry and estimates the equilibrium-line altitude (ELA) and accumulation-area ratio (AAR) of glaciers.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Glacier Outlines File".to_owned(),
+            flags: vec!["--outlines".to_owned()],
+            description: "Input vector glacier outline polygons file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output vector glacier polygons file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Vector(
+                VectorGeometryType::Polygon,
+            )),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Hypsometry File (optional)".to_owned(),
+            flags: vec!["--out_hypsometry".to_owned()],
+            description: "Output CSV file of binned glacier hypsometry.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Csv),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "ELA Method".to_owned(),
+            flags: vec!["--method".to_owned()],
+            description: "ELA estimation method; options are 'aar' and 'aabr'.".to_owned(),
+            parameter_type: ParameterType::OptionList(vec!["aar".to_owned(), "aabr".to_owned()]),
+            default_value: Some("aar".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Accumulation-Area Ratio".to_owned(),
+            flags: vec!["--aar".to_owned()],
+            description: "Accumulation-area ratio (0-1) used by the 'aar' method.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.58".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Balance Ratio".to_owned(),
+            flags: vec!["--balance_ratio".to_owned()],
+            description: "Ratio of the ablation to accumulation mass-balance gradients used by the 'aabr' method.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.75".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Elevation Bin Size".to_owned(),
+            flags: vec!["--bin_size".to_owned()],
+            description: "Elevation bin size, in DEM z units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("50.0".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif --outlines=glaciers.shp -o=glacier_ela.shp --method=aar --aar=0.6
+>>.*{0} -r={1} -v --wd=\"*path*to*data*\" --dem=DEM.tif --outlines=glaciers.shp -o=glacier_ela.shp --out_hypsometry=hypsometry.csv --method=aabr --balance_ratio=1.75 --bin_size=25.0", short_exe, name).replace("*", &sep);
+
+        GlacierHypsometry {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for GlacierHypsometry {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let mut outlines_file = parsed.get_string("outlines").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut hypsometry_file = parsed.get_string("out_hypsometry").unwrap_or_default();
+        let mut use_aabr = false;
+        let aar = parsed.get_f64("aar").unwrap_or(0.58f64);
+        let balance_ratio = parsed.get_f64("balance_ratio").unwrap_or(1.75f64);
+        let bin_size = parsed.get_f64("bin_size").unwrap_or(50f64);
+
+        if let Some(value) = parsed.get_string("method") {
+            let method = value.to_string().to_lowercase();
+            use_aabr = method.contains("aabr");
+        }
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        dem_file = resolve_input_path(&dem_file, working_directory)?;
+        outlines_file = resolve_path(&outlines_file, working_directory);
+        output_file = resolve_path(&output_file, working_directory);
+        hypsometry_file = resolve_path(&hypsometry_file, working_directory);
+        if aar <= 0f64 || aar >= 1f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The accumulation-area ratio (--aar) must be between 0 and 1.",
+            ));
+        }
+        if balance_ratio <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The balance ratio (--balance_ratio) must be greater than zero.",
+            ));
+        }
+        if bin_size <= 0f64 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The elevation bin size (--bin_size) must be greater than zero.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Raster::new(&dem_file, "r")?;
+        let outlines = read_outlines(&outlines_file)?;
+
+        let start = Instant::now();
+
+        let rows = dem.configs.rows as isize;
+        let columns = dem.configs.columns as isize;
+        let nodata = dem.configs.nodata;
+        let cell_area = dem.configs.resolution_x * dem.configs.resolution_y;
+
+        if verbose {
+            println!("Rasterizing glacier outlines...")
+        };
+        let glacier_ids = rasterize_outlines(&outlines, &dem)?;
+        let mut elevations: Vec<Vec<f64>> = vec![vec![]; outlines.num_records];
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                let id = glacier_ids.get_value(row, col);
+                if id >= 0 {
+                    let z = dem.get_value(row, col);
+                    if !is_nodata(z, nodata) {
+                        elevations[id as usize].push(z);
+                    }
+                }
+            }
+        }
+
+        let mut output = Shapefile::initialize_using_file(&output_file, &outlines, outlines.header.shape_type, true)?;
+        output.attributes.add_field(&AttributeField::new("GLAC_ID", FieldDataType::Int, 7u8, 0u8));
+        output.attributes.add_field(&AttributeField::new("AREA", FieldDataType::Real, 16u8, 3u8));
+        output.attributes.add_field(&AttributeField::new("Z_MIN", FieldDataType::Real, 10u8, 3u8));
+        output.attributes.add_field(&AttributeField::new("Z_MAX", FieldDataType::Real, 10u8, 3u8));
+        output.attributes.add_field(&AttributeField::new("Z_MEAN", FieldDataType::Real, 10u8, 3u8));
+        output.attributes.add_field(&AttributeField::new("Z_MED", FieldDataType::Real, 10u8, 3u8));
+        output.attributes.add_field(&AttributeField::new("HI", FieldDataType::Real, 8u8, 5u8));
+        output.attributes.add_field(&AttributeField::new("ELA", FieldDataType::Real, 10u8, 3u8));
+        output.attributes.add_field(&AttributeField::new("AAR", FieldDataType::Real, 8u8, 5u8));
+
+        let mut hypsometry_lines = vec![];
+        for record_num in 0..outlines.num_records {
+            let record = outlines.get_record(record_num);
+            output.add_record(record.clone());
+            let mut atts = outlines.attributes.get_record(record_num);
+            atts.push(FieldData::Int(record_num as i32 + 1));
+
+            let z = &mut elevations[record_num];
+            if z.is_empty() {
+                atts.push(FieldData::Real(0f64));
+                for _ in 0..7 {
+                    atts.push(FieldData::Null);
+                }
+                output.attributes.add_record(atts, false);
+                continue;
+            }
+            z.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            let n = z.len();
+            let z_min = z[0];
+            let z_max = z[n - 1];
+            let z_mean = z.iter().sum::<f64>() / n as f64;
+            let z_median = if n % 2 == 1 {
+                z[n / 2]
+            } else {
+                (z[n / 2 - 1] + z[n / 2]) / 2f64
+            };
+            let hi = if z_max > z_min {
+                (z_mean - z_min) / (z_max - z_min)
+            } else {
+                0f64
+            };
+
+            // binned hypsometry
+            let first_bin = (z_min / bin_size).floor();
+            let num_bins = ((z_max / bin_size).floor() - first_bin) as usize + 1;
+            let mut bin_counts = vec![0usize; num_bins];
+            for &v in z.iter() {
+                let b = (((v / bin_size).floor() - first_bin) as usize).min(num_bins - 1);
+                bin_counts[b] += 1;
+            }
+
+            let (ela, glacier_aar) = if !use_aabr {
+                // the elevation above which the AAR proportion of the area lies
+                let k = ((aar * n as f64).round() as usize).max(1).min(n);
+                (z[n - k], aar)
+            } else {
+                let bin_mid = |b: usize| (first_bin + b as f64 + 0.5) * bin_size;
+                let net_balance = |ela: f64| -> f64 {
+                    let mut sum = 0f64;
+                    for b in 0..num_bins {
+                        let dz = bin_mid(b) - ela;
+                        sum += bin_counts[b] as f64 * dz * if dz < 0f64 { balance_ratio } else { 1f64 };
+                    }
+                    sum
+                };
+                // net balance decreases monotonically with the ELA; solve by bisection
+                let (mut lower, mut upper) = (first_bin * bin_size, (first_bin + num_bins as f64) * bin_size);
+                for _ in 0..100 {
+                    let mid = (lower + upper) / 2f64;
+                    if net_balance(mid) > 0f64 {
+                        lower = mid;
+                    } else {
+                        upper = mid;
+                    }
+                }
+                let ela = (lower + upper) / 2f64;
+                let num_above = z.iter().filter(|&&v| v >= ela).count();
+                (ela, num_above as f64 / n as f64)
+            };
+
+            atts.push(FieldData::Real(n as f64 * cell_area));
+            atts.push(FieldData::Real(z_min));
+            atts.push(FieldData::Real(z_max));
+            atts.push(FieldData::Real(z_mean));
+            atts.push(FieldData::Real(z_median));
+            atts.push(FieldData::Real(hi));
+            atts.push(FieldData::Real(ela));
+            atts.push(FieldData::Real(glacier_aar));
+            output.attributes.add_record(atts, false);
+
+            if !hypsometry_file.is_empty() {
+                let mut num_above = n;
+                for b in 0..num_bins {
+                    let bin_min = (first_bin + b as f64) * bin_size;
+                    hypsometry_lines.push(format!(
+                        "{},{},{},{},{}",
+                        record_num + 1,
+                        bin_min,
+                        bin_min + bin_size,
+                        bin_counts[b] as f64 * cell_area,
+                        num_above as f64 / n as f64
+                    ));
+                    num_above -= bin_counts[b];
+                }
+            }
+
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * (record_num + 1) as f64 / outlines.num_records as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        if !hypsometry_file.is_empty() {
+            let f = File::create(&hypsometry_file)?;
+            let mut writer = BufWriter::new(f);
+            writer.write_all("GLAC_ID,BIN_MIN,BIN_MAX,AREA,FRAC_ABOVE\n".as_bytes())?;
+            for line in &hypsometry_lines {
+                writer.write_all(format!("{}\n", line).as_bytes())?;
+            }
+            let _ = writer.flush();
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+// Glacier outline handling shared by the GlacierFlowlines and GlacierHypsometry tools.
+
+use whitebox_common::algorithms::point_in_poly;
+use whitebox_common::structures::{Array2D, Point2D};
+use whitebox_raster::Raster;
+use whitebox_vector::{ShapeType, Shapefile};
+use std::io::{Error, ErrorKind};
+
+/// Reads glacier outline polygons, returning an error if the file is not of a polygon shape type.
+pub(super) fn read_outlines(file_name: &str) -> Result<Shapefile, Error> {
+    let outlines = Shapefile::read(file_name)?;
+    if outlines.header.shape_type.base_shape_type() != ShapeType::Polygon {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "The input glacier outlines vector data must be of POLYGON base shape type.",
+        ));
+    }
+    Ok(outlines)
+}
+
+/// Rasterizes glacier outlines onto the grid of a DEM. Each grid cell whose centre lies within an
+/// outline (and outside of its holes) is assigned the outline's record number, and all other cells
+/// are assigned -1. Where outlines overlap, the later record takes precedence.
+pub(super) fn rasterize_outlines(outlines: &Shapefile, dem: &Raster) -> Result<Array2D<i32>, Error> {
+    let rows = dem.configs.rows as isize;
+    let columns = dem.configs.columns as isize;
+    let mut glacier_ids: Array2D<i32> = Array2D::new(rows, columns, -1i32, -1i32)?;
+    for record_num in 0..outlines.num_records {
+        let record = outlines.get_record(record_num);
+        if record.shape_type == ShapeType::Null {
+            continue;
+        }
+        let top = dem.get_row_from_y(record.y_max).max(0);
+        let bottom = dem.get_row_from_y(record.y_min).min(rows - 1);
+        let left = dem.get_column_from_x(record.x_min).max(0);
+        let right = dem.get_column_from_x(record.x_max).min(columns - 1);
+        let mut parts = vec![];
+        for part in 0..record.num_parts as usize {
+            let part_start = record.parts[part] as usize;
+            let part_end = if part < record.num_parts as usize - 1 {
+                record.parts[part + 1] as usize
+            } else {
+                record.num_points as usize
+            };
+            parts.push((part_start, part_end));
+        }
+        for row in top..=bottom {
+            let y = dem.get_y_from_row(row);
+            for col in left..=right {
+                let p = Point2D::new(dem.get_x_from_column(col), y);
+                let mut count = 0;
+                for &(start, end) in &parts {
+                    if point_in_poly(&p, &record.points[start..end]) {
+                        count += 1;
+                    }
+                }
+                if count % 2 == 1 {
+                    glacier_ids.set_value(row, col, record_num as i32);
+                }
+            }
+        }
+    }
+    Ok(glacier_ids)
+}
@@ -2,21 +2,20 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 22/06/2017
-Last Modified: 03/09/2020
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a hillshade operation (also called shaded relief) on an input digital elevation model (DEM).
 /// The user must specify the  name of the input DEM and the output hillshade image name. Other parameters that must
@@ -40,6 +39,10 @@ use std::thread;
 /// are the illumination source altitude and azimuth respectively. Slope and aspect are calculated using
 /// Horn's (1981) 3rd-order finate difference method.
 ///
+/// When the tool is run with the `--compute=gpu` flag and WhiteboxTools has been built with the
+/// `gpu` feature, the hillshade is calculated on the GPU, in single precision. If no suitable GPU
+/// is found, the tool falls back to the CPU.
+///
 /// # Reference
 /// Gallant, J. C., and J. P. Wilson, 2000, Primary topographic attributes, in Terrain Analysis: Principles
 /// and Applications, edited by J. P. Wilson and J. C. Gallant pp. 51-86, John Wiley, Hoboken, N.J.
@@ -64,7 +67,7 @@ impl Hillshade {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -174,79 +177,14 @@ impl WhiteboxTool for Hillshade {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut azimuth = 315.0f64;
-        let mut altitude = 30.0f64;
-        let mut z_factor = -1f64;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-azimuth" {
-                if keyval {
-                    azimuth = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    azimuth = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-altitude" {
-                if keyval {
-                    altitude = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    altitude = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-zfactor" {
-                if keyval {
-                    z_factor = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    z_factor = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut azimuth = parsed.get_f64("azimuth").unwrap_or(315.0f64);
+        let mut altitude = parsed.get_f64("altitude").unwrap_or(30.0f64);
+        let z_factor = parsed.get_f64("zfactor").unwrap_or(-1f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -259,17 +197,11 @@ impl WhiteboxTool for Hillshade {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -292,84 +224,105 @@ impl WhiteboxTool for Hillshade {
         let out_nodata = output.configs.nodata;
         let rows = input.configs.rows as isize;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let mut z_factor_array = Vec::with_capacity(rows as usize);
+        if input.is_in_geographic_coordinates() && z_factor < 0.0 {
+            // calculate a new z-conversion factor
+            for row in 0..rows {
+                let lat = input.get_y_from_row(row);
+                z_factor_array.push(1.0 / (111320.0 * lat.cos()));
+            }
+        } else {
+            let z = if z_factor < 0.0 { 1.0 } else { z_factor };
+            z_factor_array = vec![z; rows as usize];
         }
+        let z_factor_array = Arc::new(z_factor_array);
+
+        let gpu_rows = if gpu::gpu_requested() {
+            let kernel = gpu::FocalKernel::Hillshade {
+                z_factors: &z_factor_array,
+                res: input.configs.resolution_x,
+                azimuth: azimuth,
+                altitude: altitude,
+            };
+            gpu::run_focal(&input, &kernel, out_nodata, verbose)
+        } else {
+            None
+        };
+
         let (tx, rx) = mpsc::channel();
-        for tid in 0..num_procs {
-            let input = input.clone();
-            let tx1 = tx.clone();
-            thread::spawn(move || {
-                let nodata = input.configs.nodata;
-                let columns = input.configs.columns as isize;
-                let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
-                let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
-                let mut n: [f64; 8] = [0.0; 8];
-                let mut z: f64;
-                let (mut term1, mut term2, mut term3): (f64, f64, f64);
-                let (mut fx, mut fy): (f64, f64);
-                let mut tan_slope: f64;
-                let mut aspect: f64;
-                let half_pi = PI / 2f64;
-                let mut z_factor_array = Vec::with_capacity(rows as usize);
-                if input.is_in_geographic_coordinates() && z_factor < 0.0 {
-                    // calculate a new z-conversion factor
-                    for row in 0..rows {
-                        let lat = input.get_y_from_row(row);
-                        z_factor_array.push(1.0 / (111320.0 * lat.cos()));
-                    }
-                } else {
-                    if z_factor < 0.0 {
-                        z_factor = 1.0;
-                    }
-                    z_factor_array = vec![z_factor; rows as usize];
+        if let Some(gpu_rows) = gpu_rows {
+            for (row, mut data) in gpu_rows.into_iter().enumerate() {
+                reporter.check_cancelled()?;
+                for z in data.iter_mut().filter(|z| !is_nodata(**z, out_nodata)) {
+                    *z = (*z * 32767.0).max(0.0).round();
                 }
-                for row in (0..rows).filter(|r| r % num_procs == tid) {
-                    let mut data = vec![out_nodata; columns as usize];
-                    for col in 0..columns {
-                        z = input.get_value(row, col);
-                        if z != nodata {
-                            z = z * z_factor_array[row as usize];
-                            for c in 0..8 {
-                                n[c] = input.get_value(row + d_y[c], col + d_x[c]);
-                                if n[c] != nodata {
-                                    n[c] = n[c] * z_factor_array[row as usize];
+                tx.send((row as isize, data)).unwrap();
+            }
+        } else {
+            for tid in 0..num_procs {
+                let input = input.clone();
+                let z_factor_array = z_factor_array.clone();
+                let tx1 = tx.clone();
+                spawn(move || {
+                    let nodata = input.configs.nodata;
+                    let columns = input.configs.columns as isize;
+                    let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+                    let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+                    let mut n: [f64; 8] = [0.0; 8];
+                    let mut z: f64;
+                    let (mut term1, mut term2, mut term3): (f64, f64, f64);
+                    let (mut fx, mut fy): (f64, f64);
+                    let mut tan_slope: f64;
+                    let mut aspect: f64;
+                    let half_pi = PI / 2f64;
+                    for row in (0..rows).filter(|r| r % num_procs == tid) {
+                        let mut data = vec![out_nodata; columns as usize];
+                        for col in 0..columns {
+                            z = input.get_value(row, col);
+                            if !is_nodata(z, nodata) {
+                                z = z * z_factor_array[row as usize];
+                                for c in 0..8 {
+                                    n[c] = input.get_value(row + d_y[c], col + d_x[c]);
+                                    if !is_nodata(n[c], nodata) {
+                                        n[c] = n[c] * z_factor_array[row as usize];
+                                    } else {
+                                        n[c] = z;
+                                    }
+                                }
+                                // calculate slope and aspect
+                                fy = (n[6] - n[4] + 2.0 * (n[7] - n[3]) + n[0] - n[2]) / eight_grid_res;
+                                fx = (n[2] - n[4] + 2.0 * (n[1] - n[5]) + n[0] - n[6]) / eight_grid_res;
+                                tan_slope = (fx * fx + fy * fy).sqrt();
+                                if tan_slope < 0.00017 {
+                                    tan_slope = 0.00017;
+                                }
+                                aspect = if fx != 0f64 {
+                                    PI - ((fy / fx).atan()) + half_pi * (fx / (fx).abs())
                                 } else {
-                                    n[c] = z;
+                                    PI
+                                };
+                                term1 = tan_slope / (1f64 + tan_slope * tan_slope).sqrt();
+                                term2 = sin_theta / tan_slope;
+                                term3 = cos_theta * (azimuth - aspect).sin();
+                                z = term1 * (term2 - term3);
+                                // } else {
+                                //     z = 0.5;
+                                // }
+                                z = z * 32767.0;
+                                if z < 0.0 {
+                                    z = 0.0;
                                 }
+                                data[col as usize] = z.round();
                             }
-                            // calculate slope and aspect
-                            fy = (n[6] - n[4] + 2.0 * (n[7] - n[3]) + n[0] - n[2]) / eight_grid_res;
-                            fx = (n[2] - n[4] + 2.0 * (n[1] - n[5]) + n[0] - n[6]) / eight_grid_res;
-                            tan_slope = (fx * fx + fy * fy).sqrt();
-                            if tan_slope < 0.00017 {
-                                tan_slope = 0.00017;
-                            }
-                            aspect = if fx != 0f64 {
-                                PI - ((fy / fx).atan()) + half_pi * (fx / (fx).abs())
-                            } else {
-                                PI
-                            };
-                            term1 = tan_slope / (1f64 + tan_slope * tan_slope).sqrt();
-                            term2 = sin_theta / tan_slope;
-                            term3 = cos_theta * (azimuth - aspect).sin();
-                            z = term1 * (term2 - term3);
-                            // } else {
-                            //     z = 0.5;
-                            // }
-                            z = z * 32767.0;
-                            if z < 0.0 {
-                                z = 0.0;
-                            }
-                            data[col as usize] = z.round();
+                        }
+                        if tx1.send((row, data)).is_err() {
+                            return;
                         }
                     }
-                    tx1.send((row, data)).unwrap();
-                }
-            });
+                });
+            }
         }
 
         let mut histo: [f64; 32768] = [0.0; 32768];
@@ -378,7 +331,7 @@ impl WhiteboxTool for Hillshade {
             let data = rx.recv().expect("Error receiving data from thread.");
             let mut bin: usize;
             for col in 0..data.1.len() {
-                if data.1[col] != out_nodata {
+                if !is_nodata(data.1[col], out_nodata) {
                     bin = data.1[col] as usize;
                     histo[bin] += 1.0;
                     num_cells += 1.0;
@@ -386,10 +339,11 @@ impl WhiteboxTool for Hillshade {
             }
             output.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    reporter.progress("Performing analysis", progress);
                     old_progress = progress;
                 }
             }
@@ -8,16 +8,15 @@ License: MIT
 NOTES: The tool should have the option to output a distance raster as well.
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool calculates the horizon angle (*Sx*), i.e. the maximum slope along a specified azimuth (0-360 degrees) for
 /// each grid cell in an input digital elevation model (DEM). Horizon angle is sometime referred to as the maximum upwind
@@ -69,7 +68,7 @@ impl HorizonAngle {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -100,7 +99,7 @@ impl HorizonAngle {
             description: "Optional maximum search distance (unspecified if none; in xy units). Minimum value is 5 x cell size."
                 .to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("100.0".to_owned()),
+            default_value: None,
             optional: true,
         });
 
@@ -169,66 +168,13 @@ impl WhiteboxTool for HorizonAngle {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut azimuth = 0.0f32;
-        let mut max_dist = f32::INFINITY;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-azimuth" {
-                if keyval {
-                    azimuth = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    azimuth = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-max_dist" {
-                max_dist = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let azimuth = parsed.get_f64("azimuth").map(|v| v as f32).unwrap_or(0.0f32);
+        let mut max_dist = parsed.get_f64("max_dist").map(|v| v as f32).unwrap_or(f32::INFINITY);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -241,17 +187,11 @@ impl WhiteboxTool for HorizonAngle {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -299,17 +239,13 @@ impl WhiteboxTool for HorizonAngle {
         drop(inputf64);
 
         // Now perform the filter
-        let mut num_procs = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 // The ray-tracing operation can be viewed as a linear maximum filter. The first step
                 // is to create the filter offsets and calculate the offset distances.
 
@@ -402,7 +338,7 @@ impl WhiteboxTool for HorizonAngle {
                     let mut data: Vec<f64> = vec![nodata; columns as usize];
                     for col in 0..columns {
                         current_elev = input.get_value(row, col);
-                        if current_elev != nodata_f32 {
+                        if !is_nodata(current_elev as f64, nodata_f32 as f64) {
                             // Run down the offsets of the ray
                             current_max_slope = a_small_value;
                             current_max_elev = a_small_value;
@@ -417,11 +353,11 @@ impl WhiteboxTool for HorizonAngle {
                                 z1 = input.get_value(y1, x1);
                                 z2 = input.get_value(y2, x2);
 
-                                if z1 == nodata_f32 && z2 == nodata_f32 {
+                                if is_nodata(z1 as f64, nodata_f32 as f64) && is_nodata(z2 as f64, nodata_f32 as f64) {
                                     break; // We're likely off the grid.
-                                } else if z1 == nodata_f32 {
+                                } else if is_nodata(z1 as f64, nodata_f32 as f64) {
                                     z1 = z2;
-                                } else if z2 == nodata_f32 {
+                                } else if is_nodata(z2 as f64, nodata_f32 as f64) {
                                     z2 = z2;
                                 }
 
@@ -450,7 +386,9 @@ impl WhiteboxTool for HorizonAngle {
                             }
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -460,10 +398,11 @@ impl WhiteboxTool for HorizonAngle {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
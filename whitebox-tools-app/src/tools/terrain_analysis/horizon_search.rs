@@ -0,0 +1,172 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+// The multi-directional horizon search shared by the Openness and SkyViewFactor tools.
+
+use crate::tools::ProgressReporter;
+use whitebox_raster::Raster;
+use whitebox_common::structures::Array2D;
+use std::f64;
+use std::f64::consts::PI;
+use std::io::Error;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// Returns the x and y cell sizes of a DEM in ground units. The cell sizes of DEMs in
+/// geographic coordinates are converted to metres at the latitude of the DEM's centre.
+pub(super) fn ground_cell_sizes(input: &Raster) -> (f64, f64) {
+    let mut cell_size_x = input.configs.resolution_x;
+    let mut cell_size_y = input.configs.resolution_y;
+    if input.is_in_geographic_coordinates() {
+        let mid_lat = ((input.configs.north + input.configs.south) / 2.0).to_radians();
+        cell_size_x = cell_size_x * (111320.0 * mid_lat.cos());
+        cell_size_y = cell_size_y * 111320.0;
+    }
+    (cell_size_x, cell_size_y)
+}
+
+/// Traces rays outward from each grid cell of a DEM in `num_directions` equally spaced
+/// azimuths, starting at north, out to a distance of `max_dist` ground units. Along each
+/// ray, the largest and smallest elevation angles (in radians) from the cell to the terrain
+/// are found. The angles of a direction are zero if the ray leaves the grid immediately or
+/// encounters only nodata cells.
+///
+/// For each valid cell, `reducer` is called with the maximum and minimum angles of each
+/// direction and a slice of length `num_outputs` into which it writes the cell's output
+/// values. One array is returned for each output; nodata cells in the DEM hold the DEM's
+/// nodata value in each output.
+pub(super) fn horizon_search<F>(
+    input: &Arc<Raster>,
+    num_directions: usize,
+    max_dist: f64,
+    num_outputs: usize,
+    reducer: F,
+    verbose: bool,
+    reporter: &dyn ProgressReporter,
+) -> Result<Vec<Array2D<f64>>, Error>
+where
+    F: Fn(&[f64], &[f64], &mut [f64]) + Send + Sync + 'static,
+{
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+    let (cell_size_x, cell_size_y) = ground_cell_sizes(input);
+
+    // The cell offsets along each ray are found by stepping along the ray at half the
+    // smaller cell size and keeping each newly encountered cell.
+    let step = cell_size_x.min(cell_size_y) / 2.0;
+    let mut rays: Vec<Vec<(isize, isize, f64)>> = Vec::with_capacity(num_directions);
+    for d in 0..num_directions {
+        let azimuth = 2.0 * PI * d as f64 / num_directions as f64;
+        let (sin_az, cos_az) = azimuth.sin_cos();
+        let mut ray = vec![];
+        let mut k = 1;
+        while k as f64 * step <= max_dist {
+            let dist = k as f64 * step;
+            let dc = (dist * sin_az / cell_size_x).round() as isize;
+            let dr = (-dist * cos_az / cell_size_y).round() as isize;
+            let is_new = match ray.last() {
+                Some(&(r, c, _)) => r != dr || c != dc,
+                None => dr != 0 || dc != 0,
+            };
+            if is_new {
+                let ground_dist = (dc as f64 * cell_size_x).hypot(dr as f64 * cell_size_y);
+                if ground_dist <= max_dist {
+                    ray.push((dr, dc, ground_dist));
+                }
+            }
+            k += 1;
+        }
+        rays.push(ray);
+    }
+    let rays = Arc::new(rays);
+    let reducer = Arc::new(reducer);
+
+    let configs = whitebox_common::configs::get_configs()?;
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let input = input.clone();
+        let rays = rays.clone();
+        let reducer = reducer.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            let mut max_angles = vec![0f64; num_directions];
+            let mut min_angles = vec![0f64; num_directions];
+            let mut values = vec![0f64; num_outputs];
+            let (mut z, mut zn, mut angle): (f64, f64, f64);
+            let (mut rn, mut cn): (isize, isize);
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let mut data = vec![vec![nodata; columns as usize]; num_outputs];
+                for col in 0..columns {
+                    z = input.get_value(row, col);
+                    if input.is_nodata(z) {
+                        continue;
+                    }
+                    for d in 0..num_directions {
+                        max_angles[d] = f64::NEG_INFINITY;
+                        min_angles[d] = f64::INFINITY;
+                        for &(dr, dc, dist) in rays[d].iter() {
+                            rn = row + dr;
+                            cn = col + dc;
+                            if rn < 0 || rn >= rows || cn < 0 || cn >= columns {
+                                break;
+                            }
+                            zn = input.get_value(rn, cn);
+                            if input.is_nodata(zn) {
+                                continue;
+                            }
+                            angle = ((zn - z) / dist).atan();
+                            if angle > max_angles[d] {
+                                max_angles[d] = angle;
+                            }
+                            if angle < min_angles[d] {
+                                min_angles[d] = angle;
+                            }
+                        }
+                        if max_angles[d] == f64::NEG_INFINITY {
+                            max_angles[d] = 0f64;
+                            min_angles[d] = 0f64;
+                        }
+                    }
+                    reducer(&max_angles, &min_angles, &mut values);
+                    for i in 0..num_outputs {
+                        data[i][col as usize] = values[i];
+                    }
+                }
+                if tx.send((row, data)).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    let mut outputs = Vec::with_capacity(num_outputs);
+    for _ in 0..num_outputs {
+        outputs.push(Array2D::new(rows, columns, nodata, nodata)?);
+    }
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+    for r in 0..rows {
+        let (row, data) = rx.recv().expect("Error receiving data from thread.");
+        for (i, values) in data.into_iter().enumerate() {
+            outputs[i].set_row_data(row, values);
+        }
+        reporter.check_cancelled()?;
+        if verbose {
+            progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Progress", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    Ok(outputs)
+}
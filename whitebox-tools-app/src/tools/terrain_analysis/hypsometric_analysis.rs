@@ -6,7 +6,7 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use crate::tools::*;
@@ -138,47 +138,12 @@ impl WhiteboxTool for HypsometricAnalysis {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files_str = String::new();
-        let mut watershed_files_str = "".to_string();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                input_files_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-watershed" {
-                watershed_files_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files_str = parsed.get_string("inputs").unwrap_or_default();
+        let watershed_files_str = parsed.get_string("watershed").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -191,8 +156,6 @@ impl WhiteboxTool for HypsometricAnalysis {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -210,9 +173,7 @@ impl WhiteboxTool for HypsometricAnalysis {
                                 "There is something incorrect about the input DEM files. At least one input DEM is required to operate this tool."));
         }
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         let f = File::create(output_file.clone())?;
         let mut writer = BufWriter::new(f);
@@ -244,12 +205,9 @@ impl WhiteboxTool for HypsometricAnalysis {
         if watershed_files_str.is_empty() {
             for i in 0..num_files {
                 let mut input_file = input_files[i].to_string();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 let input = Raster::new(&input_file, "r")?;
 
-                
                 let rows = input.configs.rows as isize;
                 let columns = input.configs.columns as isize;
                 let nodata = input.configs.nodata;
@@ -271,7 +229,7 @@ impl WhiteboxTool for HypsometricAnalysis {
                 for row in 0..rows {
                     for col in 0..columns {
                         val = input.get_value(row, col);
-                        if val != nodata {
+                        if !is_nodata(val, nodata) {
                             // bin = ((val - min) / bin_width).floor() as usize;
                             bin = (100f64 * (val - min) / range).floor() as usize;
                             // if bin >= num_bins { bin = num_bins - 1; }
@@ -280,10 +238,11 @@ impl WhiteboxTool for HypsometricAnalysis {
                             total += val;
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Binning the data: {}%", progress);
+                            reporter.progress("Binning the data", progress);
                             old_progress = progress;
                         }
                     }
@@ -338,9 +297,7 @@ impl WhiteboxTool for HypsometricAnalysis {
 
             for i in 0..num_files {
                 let mut input_file = input_files[i].to_string();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 let input = Raster::new(&input_file, "r")?;
                 if num_files == 1 {
                     writer.write_all(
@@ -356,16 +313,13 @@ impl WhiteboxTool for HypsometricAnalysis {
                 let nodata = input.configs.nodata;
 
                 let mut watershed_file = watershed_files[i].to_string();
-                if !watershed_file.contains(&sep) {
-                    watershed_file = format!("{}{}", working_directory, watershed_file);
-                }
+                watershed_file = resolve_input_path(&watershed_file, working_directory)?;
                 let watershed = Raster::new(&watershed_file, "r")?;
                 let ws_nodata = watershed.configs.nodata;
                 if watershed.configs.rows as isize != rows
                     || watershed.configs.columns as isize != columns
                 {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                            "The input DEM and watershed rasters should have the same extents (rows and columns)."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input DEM and watershed rasters should have the same extents (rows and columns)."));
                 }
 
                 let watershed_min = watershed.configs.minimum;
@@ -381,9 +335,9 @@ impl WhiteboxTool for HypsometricAnalysis {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             val = watershed.get_value(row, col);
-                            if val != 0f64 && val != ws_nodata {
+                            if val != 0f64 && !is_nodata(val, ws_nodata) {
                                 watershed_id = (val - watershed_min) as usize;
                                 if z < min_elevs[watershed_id] {
                                     min_elevs[watershed_id] = z;
@@ -394,10 +348,11 @@ impl WhiteboxTool for HypsometricAnalysis {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Processing watershed data: {}%", progress);
+                            reporter.progress("Processing watershed data", progress);
                             old_progress = progress;
                         }
                     }
@@ -434,9 +389,9 @@ impl WhiteboxTool for HypsometricAnalysis {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             val = watershed.get_value(row, col);
-                            if val != 0f64 && val != ws_nodata {
+                            if val != 0f64 && !is_nodata(val, ws_nodata) {
                                 watershed_id = (val - watershed_min) as usize;
                                 bin = ((z - min_elevs[watershed_id]) / bin_widths[watershed_id])
                                     .floor() as usize;
@@ -445,10 +400,11 @@ impl WhiteboxTool for HypsometricAnalysis {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Binning the data: {}%", progress);
+                            reporter.progress("Binning the data", progress);
                             old_progress = progress;
                         }
                     }
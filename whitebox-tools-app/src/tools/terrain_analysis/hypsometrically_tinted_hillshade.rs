@@ -9,18 +9,17 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use rand::prelude::*;
 use rand::rngs::SmallRng;
 use rand_distr::Uniform;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool creates a hypsometrically tinted shaded relief (Swiss hillshading) image from an input digital elevation model (DEM).
 /// The tool combines a colourized version of the DEM with varying illumination provided by a hillshade image, to
@@ -69,7 +68,7 @@ impl HypsometricallyTintedHillshade {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -175,7 +174,7 @@ impl HypsometricallyTintedHillshade {
 
         parameters.push(ToolParameter {
             name: "Full 360-degree hillshade mode?".to_owned(),
-            flags: vec!["--full_mode".to_owned()],
+            flags: vec!["--fullmode".to_owned(), "--full_mode".to_owned()],
             description:
                 "Optional flag indicating whether to use full 360-degrees of illumination sources."
                     .to_owned(),
@@ -249,153 +248,51 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut multidirection360mode = false;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let multidirection360mode = parsed.get_bool("full_mode");
         // let mut azimuth = 315.0f64;
-        let mut altitude = 45.0f64;
-        let mut z_factor = -1f64;
+        let mut altitude = parsed.get_f64("altitude").unwrap_or(45.0f64);
+        let mut z_factor = parsed.get_f64("zfactor").unwrap_or(-1f64);
         let mut hs_alpha = 0.5f32;
         let mut brightness = 0.5f32;
         let mut atmospheric_alpha = 0.0f32;
         let mut palette = String::from("atlas");
-        let mut reverse_palette = false;
+        let reverse_palette = parsed.get_bool("reverse");
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_f64("hs_weight") {
+            hs_alpha = value as f32;
+            if hs_alpha < 0.0 {
+                hs_alpha = 0.0;
+            }
+            if hs_alpha > 1.0 {
+                hs_alpha = 1.0;
+            }
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_f64("brightness") {
+            brightness = value as f32;
+            if brightness < 0.0 {
+                brightness = 0.0;
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            // } else if flag_val == "-azimuth" {
-            //     if keyval {
-            //         azimuth = vec[1]
-            //             .to_string()
-            //             .parse::<f64>()
-            //             .expect(&format!("Error parsing {}", flag_val));
-            //     } else {
-            //         azimuth = args[i + 1]
-            //             .to_string()
-            //             .parse::<f64>()
-            //             .expect(&format!("Error parsing {}", flag_val));
-            //     }
-            } else if flag_val == "-altitude" {
-                if keyval {
-                    altitude = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    altitude = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-hs_weight" {
-                hs_alpha = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if hs_alpha < 0.0 {
-                    hs_alpha = 0.0;
-                }
-                if hs_alpha > 1.0 {
-                    hs_alpha = 1.0;
-                }
-            } else if flag_val == "-brightness" {
-                brightness = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if brightness < 0.0 {
-                    brightness = 0.0;
-                }
-                if brightness > 1.0 {
-                    brightness = 1.0;
-                }
-            } else if flag_val == "-atmospheric" {
-                atmospheric_alpha = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-                if atmospheric_alpha < 0.0 {
-                    atmospheric_alpha = 0.0;
-                }
-                if atmospheric_alpha > 1.0 {
-                    atmospheric_alpha = 1.0;
-                }
-            } else if flag_val == "-palette" {
-                palette = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                palette = palette.to_lowercase();
-            } else if flag_val == "-reverse" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    reverse_palette = true;
-                }
-            } else if flag_val == "-zfactor" {
-                if keyval {
-                    z_factor = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    z_factor = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-full_mode" || flag_val == "-fullmode" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    multidirection360mode = true;
-                }
+            if brightness > 1.0 {
+                brightness = 1.0;
+            }
+        }
+        if let Some(value) = parsed.get_f64("atmospheric") {
+            atmospheric_alpha = value as f32;
+            if atmospheric_alpha < 0.0 {
+                atmospheric_alpha = 0.0;
             }
+            if atmospheric_alpha > 1.0 {
+                atmospheric_alpha = 1.0;
+            }
+        }
+        if let Some(value) = parsed.get_string("palette") {
+            palette = value.to_string();
+            palette = palette.to_lowercase();
         }
 
         if verbose {
@@ -409,17 +306,11 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         let relief_alpha = 1f32 - hs_alpha;
 
@@ -451,17 +342,13 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
         let rows = input.configs.rows as isize;
         let columns = input.configs.columns as isize;
         let mut hs: Array2D<i16> = Array2D::new(rows, columns, -32768i16, -32768i16)?;
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let nodata = input.configs.nodata;
                 let out_nodata = -32768i16;
                 let columns = input.configs.columns as isize;
@@ -508,11 +395,11 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
                     let mut data = vec![out_nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             z = z * z_factor;
                             for c in 0..8 {
                                 n[c] = input.get_value(row + dy[c], col + dx[c]);
-                                if n[c] != nodata {
+                                if !is_nodata(n[c], nodata) {
                                     n[c] = n[c] * z_factor;
                                 } else {
                                     n[c] = z;
@@ -545,7 +432,9 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
                             data[col as usize] = z.round() as i16;
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -572,10 +461,11 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
             }
             hs.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    reporter.progress("Performing analysis", progress);
                     old_progress = progress;
                 }
             }
@@ -644,7 +534,7 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
             let hs = hs.clone();
             let palette = palette.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut elev_palette = if palette.contains("atlas") {
                     vec![
                         (72f32, 135f32, 55f32),
@@ -1272,7 +1162,9 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
                             data[col as usize] = out_nodata;
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -1281,10 +1173,11 @@ impl WhiteboxTool for HypsometricallyTintedHillshade {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    reporter.progress("Performing analysis", progress);
                     old_progress = progress;
                 }
             }
@@ -0,0 +1,317 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::horizon_search::ground_cell_sizes;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool calculates the local dominance (LD) of each grid cell in an input digital elevation model
+/// (`--dem`). Local dominance (Hesse, 2016) is the mean angle at which an observer standing on the surrounding
+/// terrain looks down at (or up to) the cell. For each neighbouring cell *i* located within an annulus bounded by
+/// a minimum (`--min_radius`) and maximum (`--max_radius`) radius, measured in grid cells, the angle between the
+/// eye of an observer of height *h* (`--observer_height`), standing on the neighbouring cell, and the central
+/// cell is calculated. LD is then:
+///
+/// > LD = Σ arctan((z<sub>0</sub> - (z<sub>i</sub> + h)) / d<sub>i</sub>) / n
+///
+/// where *z<sub>0</sub>* is the elevation of the central cell, *z<sub>i</sub>* and *d<sub>i</sub>* are the
+/// elevation and horizontal distance of neighbour *i*, and *n* is the number of valid neighbours in the annulus.
+/// The output is measured in degrees. Positive values indicate that the cell is, on average, seen from above the
+/// observers' eye level and therefore dominates its surroundings.
+///
+/// Local dominance is particularly well suited to the visualization of low, subtle positive features in flat
+/// terrain, such as burial mounds, embankments, and field boundaries, and is widely used in archaeological
+/// prospection using lidar-derived DEMs. The annulus should be chosen to match the size of the features of
+/// interest; the default radii of 10 and 20 grid cells correspond to the values recommended by Hesse (2016)
+/// for DEMs with a resolution of 0.5 m. Excluding the cells nearest to the centre (the minimum radius) reduces
+/// the influence of local surface roughness. The observer height is given in the vertical units of the DEM.
+///
+/// # Reference
+/// Hesse, R. (2016). Visualisierung hochauflösender Digitaler Geländemodelle mit LiVT. In: Lieberwirth, U.,
+/// and Herzog, I. (eds.), *Computeranwendungen und Quantitative Methoden in der Archäologie*. Berlin Studies
+/// of the Ancient World, 34, 109-128.
+///
+/// # See Also
+/// `Openness`, `LocalDominance`, `DevFromMeanElev`, `RelativeTopographicPosition`
+pub struct LocalDominance {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl LocalDominance {
+    pub fn new() -> LocalDominance {
+        // public constructor
+        let name = "LocalDominance".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Calculates the local dominance of each cell in a DEM, used for visualizing subtle relief.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Minimum Radius (cells)".to_owned(),
+            flags: vec!["--min_radius".to_owned()],
+            description: "Inner radius of the annulus of observer cells, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("10".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Radius (cells)".to_owned(),
+            flags: vec!["--max_radius".to_owned()],
+            description: "Outer radius of the annulus of observer cells, in grid cells.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("20".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Observer Height".to_owned(),
+            flags: vec!["--observer_height".to_owned()],
+            description: "Height of the observer's eye above the ground, in vertical units.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("1.7".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=dominance.tif --min_radius=10 --max_radius=20 --observer_height=1.7",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        LocalDominance {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for LocalDominance {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut min_radius = parsed.get_i64("min_radius").map(|v| v as isize).unwrap_or(10isize);
+        let max_radius = parsed.get_i64("max_radius").map(|v| v as isize).unwrap_or(20isize);
+        let observer_height = parsed.get_f64("observer_height").unwrap_or(1.7f64);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        if min_radius < 1 {
+            min_radius = 1;
+        }
+        if max_radius < min_radius {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum radius (--max_radius) must be at least as large as the minimum radius (--min_radius).",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+        let (cell_size_x, cell_size_y) = ground_cell_sizes(&input);
+
+        // The observer cells are those whose centres lie within the annulus.
+        let mut offsets: Vec<(isize, isize, f64)> = vec![];
+        for dr in -max_radius..=max_radius {
+            for dc in -max_radius..=max_radius {
+                let r2 = dr * dr + dc * dc;
+                if r2 >= min_radius * min_radius && r2 <= max_radius * max_radius {
+                    let dist = (dc as f64 * cell_size_x).hypot(dr as f64 * cell_size_y);
+                    offsets.push((dr, dc, dist));
+                }
+            }
+        }
+        let offsets = Arc::new(offsets);
+
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let offsets = offsets.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let (mut z, mut zn, mut sum): (f64, f64, f64);
+                let mut n: usize;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z = input.get_value(row, col);
+                        if input.is_nodata(z) {
+                            continue;
+                        }
+                        sum = 0f64;
+                        n = 0;
+                        for &(dr, dc, dist) in offsets.iter() {
+                            zn = input.get_value(row + dr, col + dc);
+                            if !input.is_nodata(zn) {
+                                sum += ((z - zn - observer_height) / dist).atan();
+                                n += 1;
+                            }
+                        }
+                        if n > 0 {
+                            data[col as usize] = (sum / n as f64).to_degrees();
+                        }
+                    }
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "grey.plt".to_string();
+        output.configs.z_units = "degrees".to_string();
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        for r in 0..rows {
+            let (row, data) = rx.recv().expect("Error receiving data from thread.");
+            output.set_row_data(row, data);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Minimum radius: {}", min_radius));
+        output.add_metadata_entry(format!("Maximum radius: {}", max_radius));
+        output.add_metadata_entry(format!("Observer height: {}", observer_height));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
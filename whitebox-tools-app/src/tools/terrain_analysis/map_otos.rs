@@ -10,7 +10,7 @@ use whitebox_raster::*;
 // use crate::structures::Array2D;
 use crate::tools::*;
 use std::env;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::{f32, f64};
 
@@ -44,7 +44,7 @@ impl MapOffTerrainObjects {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -65,7 +65,7 @@ impl MapOffTerrainObjects {
             flags: vec!["--max_slope".to_owned()],
             description: "Maximum inter-cell absolute slope.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("40.0".to_owned()),
+            default_value: Some("90.0".to_owned()),
             optional: false,
         });
 
@@ -74,7 +74,7 @@ impl MapOffTerrainObjects {
             flags: vec!["--min_size".to_owned()],
             description: "Minimum feature size, in grid cells.".to_owned(),
             parameter_type: ParameterType::Integer,
-            default_value: Some("1".to_owned()),
+            default_value: Some("0".to_owned()),
             optional: false,
         });
 
@@ -147,66 +147,13 @@ impl WhiteboxTool for MapOffTerrainObjects {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut max_slope = f32::INFINITY;
-        let mut min_size = 0usize;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-max_slope" {
-                max_slope = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-min_size" {
-                min_size = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<usize>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut max_slope = parsed.get_f64("max_slope").map(|v| v as f32).unwrap_or(90f32);
+        let min_size = parsed.get_usize("min_size")?.unwrap_or(0usize);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -219,17 +166,11 @@ impl WhiteboxTool for MapOffTerrainObjects {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if max_slope < 1f32 {
             max_slope = 1f32;
@@ -286,7 +227,7 @@ impl WhiteboxTool for MapOffTerrainObjects {
         for row in 0..rows {
             for col in 0..columns {
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z as f64, nodata as f64) {
                     if output.get_value(row, col) == -1f64 {
                         let mut stack = vec![];
                         stack.push(GridCell::new(row, col));
@@ -300,7 +241,7 @@ impl WhiteboxTool for MapOffTerrainObjects {
                                 row_n = cell.row + dy[n];
                                 col_n = cell.column + dx[n];
                                 zn = input.get_value(row_n, col_n);
-                                if zn != nodata && output.get_value(row_n, col_n) == -1f64 {
+                                if !is_nodata(zn as f64, nodata as f64) && output.get_value(row_n, col_n) == -1f64 {
                                     if (z - zn).abs() / cellsize[n] < max_slope {
                                         stack.push(GridCell::new(row_n, col_n));
                                         output.set_value(row_n, col_n, fid);
@@ -310,10 +251,11 @@ impl WhiteboxTool for MapOffTerrainObjects {
                             }
 
                             num_cells_popped += 1f64;
+                            reporter.check_cancelled()?;
                             if verbose {
                                 progress = (100.0_f64 * num_cells_popped / num_cells) as usize;
                                 if progress != old_progress {
-                                    println!("Region growing: {}%", progress);
+                                    reporter.progress("Region growing", progress);
                                     old_progress = progress;
                                 }
                             }
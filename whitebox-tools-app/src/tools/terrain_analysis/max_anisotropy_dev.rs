@@ -6,17 +6,16 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// Calculates the maximum anisotropy (directionality) in elevation deviation over a range of spatial scales.
 pub struct MaxAnisotropyDev {
@@ -37,7 +36,7 @@ impl MaxAnisotropyDev {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -154,74 +153,31 @@ impl WhiteboxTool for MaxAnisotropyDev {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_mag_file = String::new();
-        let mut output_scale_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_mag_file = parsed.get_string("out_mag").unwrap_or_default();
+        let mut output_scale_file = parsed.get_string("out_scale").unwrap_or_default();
         let mut min_scale = 3isize;
         let mut max_scale = 100isize;
         let mut step = 2isize;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_i64("min_scale") {
+            min_scale = value as isize;
+            if min_scale < 3 {
+                min_scale = 3;
+            }
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_i64("max_scale") {
+            max_scale = value as isize;
+            if max_scale < 5 {
+                max_scale = 5;
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_mag" || flag_val == "-out_mag" {
-                output_mag_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_scale" {
-                output_scale_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-min_scale" {
-                min_scale = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-                if min_scale < 3 {
-                    min_scale = 3;
-                }
-            } else if flag_val == "-max_scale" {
-                max_scale = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-                if max_scale < 5 {
-                    max_scale = 5;
-                }
-            } else if flag_val == "-step" {
-                step = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-                if step < 1 {
-                    step = 1;
-                }
+        }
+        if let Some(value) = parsed.get_i64("step") {
+            step = value as isize;
+            if step < 1 {
+                step = 1;
             }
         }
 
@@ -236,20 +192,12 @@ impl WhiteboxTool for MaxAnisotropyDev {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_mag_file.contains(&sep) && !output_mag_file.contains("/") {
-            output_mag_file = format!("{}{}", working_directory, output_mag_file);
-        }
-        if !output_scale_file.contains(&sep) && !output_scale_file.contains("/") {
-            output_scale_file = format!("{}{}", working_directory, output_scale_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_mag_file = resolve_path(&output_mag_file, working_directory);
+        output_scale_file = resolve_path(&output_scale_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -279,7 +227,7 @@ impl WhiteboxTool for MaxAnisotropyDev {
             sum_n = 0;
             for col in 0..columns {
                 val = input[(row, col)];
-                if val == nodata {
+                if is_nodata(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -299,10 +247,11 @@ impl WhiteboxTool for MaxAnisotropyDev {
                     integral_n[(row, col)] = sum_n;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
+                    reporter.progress("Creating integral images", progress);
                     old_progress = progress;
                 }
             }
@@ -312,12 +261,8 @@ impl WhiteboxTool for MaxAnisotropyDev {
         let i2 = Arc::new(integral2); // wrap integral2 in an Arc
         let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         let mut output_mag = Raster::initialize_using_file(&output_mag_file, &input);
         let mut output_scale = Raster::initialize_using_file(&output_scale_file, &input);
@@ -342,7 +287,7 @@ impl WhiteboxTool for MaxAnisotropyDev {
                 let i2 = i2.clone();
                 let i_n = i_n.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
                     let (mut x3, mut x4, mut y3, mut y4): (isize, isize, isize, isize);
                     let mut n: i32;
@@ -369,7 +314,7 @@ impl WhiteboxTool for MaxAnisotropyDev {
 
                             for col in 0..columns {
                                 z = input_data[(row, col)];
-                                if z != nodata {
+                                if !is_nodata(z, nodata) {
                                     // left to right:
                                     // -midpoint  -middle_pane_radius  +middle_pane_radius  +midpoint
                                     //     x1             x2                  x3               x4
@@ -583,7 +528,9 @@ impl WhiteboxTool for MaxAnisotropyDev {
                             }
                         }
 
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -593,9 +540,9 @@ impl WhiteboxTool for MaxAnisotropyDev {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
                     z2 = data[col as usize];
-                    if z2 != nodata {
+                    if !is_nodata(z2, nodata) {
                         z1 = output_mag[(row, col)];
-                        if z1 != nodata {
+                        if !is_nodata(z1, nodata) {
                             if z2 * z2 > z1 * z1 {
                                 output_mag[(row, col)] = z2;
                                 output_scale[(row, col)] = midpoint as f64;
@@ -606,13 +553,11 @@ impl WhiteboxTool for MaxAnisotropyDev {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Progress (Loop {} of {}): {}%",
-                            loop_num, num_loops, progress
-                        );
+                        reporter.progress(&format!("Progress (Loop {} of {})", loop_num, num_loops), progress);
                         old_progress = progress;
                     }
                 }
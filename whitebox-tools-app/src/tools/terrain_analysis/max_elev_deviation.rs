@@ -9,14 +9,13 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to calculate the maximum deviation from mean elevation, *DEVmax* (Lindsay et al. 2015) for each
 /// grid cell in a digital elevation model (DEM) across a range specified spatial scales. *DEV*
@@ -73,7 +72,7 @@ impl MaxElevationDeviation {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -190,82 +189,31 @@ impl WhiteboxTool for MaxElevationDeviation {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_mag_file = String::new();
-        let mut output_scale_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_mag_file = parsed.get_string("out_mag").unwrap_or_default();
+        let mut output_scale_file = parsed.get_string("out_scale").unwrap_or_default();
         let mut min_scale = 1isize;
         let mut max_scale = 100isize;
         let mut step = 1isize;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_i64("min_scale") {
+            min_scale = value as isize;
+            if min_scale < 1 {
+                min_scale = 1;
+            }
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_i64("max_scale") {
+            max_scale = value as isize;
+            if max_scale < 5 {
+                max_scale = 5;
             }
-            if vec[0].to_lowercase() == "-i"
-                || vec[0].to_lowercase() == "--input"
-                || vec[0].to_lowercase() == "--dem"
-            {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-out_mag" || vec[0].to_lowercase() == "--out_mag" {
-                if keyval {
-                    output_mag_file = vec[1].to_string();
-                } else {
-                    output_mag_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-out_scale"
-                || vec[0].to_lowercase() == "--out_scale"
-            {
-                if keyval {
-                    output_scale_file = vec[1].to_string();
-                } else {
-                    output_scale_file = args[i + 1].to_string();
-                }
-            } else if vec[0].to_lowercase() == "-min_scale"
-                || vec[0].to_lowercase() == "--min_scale"
-            {
-                if keyval {
-                    min_scale = vec[1].to_string().parse::<isize>().unwrap();
-                } else {
-                    min_scale = args[i + 1].to_string().parse::<isize>().unwrap();
-                }
-                if min_scale < 1 {
-                    min_scale = 1;
-                }
-            } else if vec[0].to_lowercase() == "-max_scale"
-                || vec[0].to_lowercase() == "--max_scale"
-            {
-                if keyval {
-                    max_scale = vec[1].to_string().parse::<isize>().unwrap();
-                } else {
-                    max_scale = args[i + 1].to_string().parse::<isize>().unwrap();
-                }
-                if max_scale < 5 {
-                    max_scale = 5;
-                }
-            } else if vec[0].to_lowercase() == "-step" || vec[0].to_lowercase() == "--step" {
-                if keyval {
-                    step = vec[1].to_string().parse::<isize>().unwrap();
-                } else {
-                    step = args[i + 1].to_string().parse::<isize>().unwrap();
-                }
-                if step < 1 {
-                    step = 1;
-                }
+        }
+        if let Some(value) = parsed.get_i64("step") {
+            step = value as isize;
+            if step < 1 {
+                step = 1;
             }
         }
 
@@ -280,20 +228,12 @@ impl WhiteboxTool for MaxElevationDeviation {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_mag_file.contains(&sep) && !output_mag_file.contains("/") {
-            output_mag_file = format!("{}{}", working_directory, output_mag_file);
-        }
-        if !output_scale_file.contains(&sep) && !output_scale_file.contains("/") {
-            output_scale_file = format!("{}{}", working_directory, output_scale_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_mag_file = resolve_path(&output_mag_file, working_directory);
+        output_scale_file = resolve_path(&output_scale_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -322,7 +262,7 @@ impl WhiteboxTool for MaxElevationDeviation {
             sum_n = 0;
             for col in 0..columns {
                 val = input[(row, col)];
-                if val == nodata {
+                if is_nodata(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -342,10 +282,11 @@ impl WhiteboxTool for MaxElevationDeviation {
                     integral_n[(row, col)] = sum_n;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
+                    reporter.progress("Creating integral images", progress);
                     old_progress = progress;
                 }
             }
@@ -355,12 +296,8 @@ impl WhiteboxTool for MaxElevationDeviation {
         let i2 = Arc::new(integral2); // wrap integral2 in an Arc
         let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         let mut output_mag = Raster::initialize_using_file(&output_mag_file, &input);
         output_mag.configs.data_type = DataType::F32;
@@ -378,7 +315,7 @@ impl WhiteboxTool for MaxElevationDeviation {
                 let i2 = i2.clone();
                 let i_n = i_n.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
                     let mut n: i32;
                     let (mut mean, mut sum, mut sum_sqr): (f64, f64, f64);
@@ -397,7 +334,7 @@ impl WhiteboxTool for MaxElevationDeviation {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input_data[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -426,7 +363,9 @@ impl WhiteboxTool for MaxElevationDeviation {
                             }
                         }
 
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -436,9 +375,9 @@ impl WhiteboxTool for MaxElevationDeviation {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
                     z2 = data[col as usize];
-                    if z2 != nodata {
+                    if !is_nodata(z2, nodata) {
                         z1 = output_mag[(row, col)];
-                        if z1 != nodata {
+                        if !is_nodata(z1, nodata) {
                             if z2 * z2 > z1 * z1 {
                                 output_mag[(row, col)] = z2;
                                 output_scale[(row, col)] = midpoint as f64;
@@ -449,13 +388,11 @@ impl WhiteboxTool for MaxElevationDeviation {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Progress (Loop {} of {}): {}%",
-                            loop_num, num_loops, progress
-                        );
+                        reporter.progress(&format!("Progress (Loop {} of {})", loop_num, num_loops), progress);
                         old_progress = progress;
                     }
                 }
@@ -1,14 +1,23 @@
 // private sub-module defined in other files
+mod accumulation_curvature;
 mod aspect;
 mod average_normal_vector_angular_deviation;
+mod bathymetric_position_index;
+mod benthic_terrain_classification;
 mod circular_variance_of_aspect;
 mod contours_from_points;
 mod contours_from_raster;
 mod dev_from_mean_elev;
 mod diff_from_mean_elev;
+mod difference_curvature;
 mod directional_relief;
 mod downslope_index;
 mod embankment_mapping;
+mod fractal_terrain;
+mod gaussian_hills;
+mod glacier_flowlines;
+mod glacier_hypsometry;
+mod glacier_outlines;
 mod hypsometrically_tinted_hillshade;
 // mod drainage_preserving_smoothing;
 mod edge_density;
@@ -24,7 +33,10 @@ mod gaussian_curvature;
 mod geomorphons;
 mod hillshade;
 mod horizon_angle;
+mod horizon_search;
+mod horizontal_excess_curvature;
 mod hypsometric_analysis;
+mod local_dominance;
 mod map_otos;
 mod max_anisotropy_dev;
 mod max_anisotropy_dev_signature;
@@ -47,39 +59,60 @@ mod multiscale_std_dev_normals_signature;
 mod multiscale_topographic_position_image;
 mod num_downslope_neighbours;
 mod num_upslope_neighbours;
+mod openness;
+mod partial_derivatives;
 mod pennock_landform_class;
 mod percent_elev_range;
+mod perlin_noise_terrain;
 mod plan_curvature;
 mod prof_curvature;
 mod profile;
+mod raster_or_constant;
 mod relative_aspect;
 mod relative_stream_power_index;
 mod relative_topographic_position;
 mod remove_off_terrain_objects;
+mod ring_curvature;
+mod rotor;
 mod ruggedness_index;
 mod sediment_transport_index;
+mod shallow_landslide_susceptibility;
+mod sky_view_factor;
 mod slope;
+mod slope_position_classification;
 mod slope_vs_elev_plot;
 mod spherical_std_dev_of_normals;
 mod standard_deviation_of_slope;
 mod surface_area_ratio;
+mod synthetic_terrain;
+mod synthetic_valley;
 mod tan_curvature;
+mod terrain_adjusted_degree_days;
 mod time_in_daylight;
 mod total_curvature;
+mod vertical_excess_curvature;
 mod viewshed;
 mod visibility_index;
 mod wetness_index;
 
 // exports identifiers from private sub-modules in the current module namespace
+pub use self::accumulation_curvature::AccumulationCurvature;
 pub use self::aspect::Aspect;
 pub use self::average_normal_vector_angular_deviation::AverageNormalVectorAngularDeviation;
+pub use self::bathymetric_position_index::BathymetricPositionIndex;
+pub use self::benthic_terrain_classification::BenthicTerrainClassification;
 pub use self::circular_variance_of_aspect::CircularVarianceOfAspect;
 pub use self::contours_from_points::ContoursFromPoints;
 pub use self::contours_from_raster::ContoursFromRaster;
 pub use self::dev_from_mean_elev::DevFromMeanElev;
 pub use self::diff_from_mean_elev::DiffFromMeanElev;
+pub use self::difference_curvature::DifferenceCurvature;
 pub use self::directional_relief::DirectionalRelief;
 pub use self::downslope_index::DownslopeIndex;
+pub use self::fractal_terrain::FractalTerrain;
+pub use self::gaussian_hills::GaussianHills;
+pub use self::glacier_flowlines::GlacierFlowlines;
+pub use self::glacier_hypsometry::GlacierHypsometry;
 pub use self::hypsometrically_tinted_hillshade::HypsometricallyTintedHillshade;
 // pub use self::drainage_preserving_smoothing::DrainagePreservingSmoothing;
 pub use self::edge_density::EdgeDensity;
@@ -96,7 +129,9 @@ pub use self::gaussian_curvature::GaussianCurvature;
 pub use self::geomorphons::Geomorphons;
 pub use self::hillshade::Hillshade;
 pub use self::horizon_angle::HorizonAngle;
+pub use self::horizontal_excess_curvature::HorizontalExcessCurvature;
 pub use self::hypsometric_analysis::HypsometricAnalysis;
+pub use self::local_dominance::LocalDominance;
 pub use self::map_otos::MapOffTerrainObjects;
 pub use self::max_anisotropy_dev::MaxAnisotropyDev;
 pub use self::max_anisotropy_dev_signature::MaxAnisotropyDevSignature;
@@ -119,8 +154,10 @@ pub use self::multiscale_std_dev_normals_signature::MultiscaleStdDevNormalsSigna
 pub use self::multiscale_topographic_position_image::MultiscaleTopographicPositionImage;
 pub use self::num_downslope_neighbours::NumDownslopeNeighbours;
 pub use self::num_upslope_neighbours::NumUpslopeNeighbours;
+pub use self::openness::Openness;
 pub use self::pennock_landform_class::PennockLandformClass;
 pub use self::percent_elev_range::PercentElevRange;
+pub use self::perlin_noise_terrain::PerlinNoiseTerrain;
 pub use self::plan_curvature::PlanCurvature;
 pub use self::prof_curvature::ProfileCurvature;
 pub use self::profile::Profile;
@@ -128,16 +165,27 @@ pub use self::relative_aspect::RelativeAspect;
 pub use self::relative_stream_power_index::StreamPowerIndex;
 pub use self::relative_topographic_position::RelativeTopographicPosition;
 pub use self::remove_off_terrain_objects::RemoveOffTerrainObjects;
+pub use self::ring_curvature::RingCurvature;
+pub use self::rotor::Rotor;
 pub use self::ruggedness_index::RuggednessIndex;
 pub use self::sediment_transport_index::SedimentTransportIndex;
+pub use self::shallow_landslide_susceptibility::ShallowLandslideSusceptibility;
+pub use self::sky_view_factor::SkyViewFactor;
 pub use self::slope::Slope;
+pub use self::slope_position_classification::SlopePositionClassification;
 pub use self::slope_vs_elev_plot::SlopeVsElevationPlot;
 pub use self::spherical_std_dev_of_normals::SphericalStdDevOfNormals;
 pub use self::standard_deviation_of_slope::StandardDeviationOfSlope;
 pub use self::surface_area_ratio::SurfaceAreaRatio;
+pub use self::synthetic_valley::SyntheticValley;
 pub use self::tan_curvature::TangentialCurvature;
+pub use self::terrain_adjusted_degree_days::TerrainAdjustedDegreeDays;
 pub use self::time_in_daylight::TimeInDaylight;
 pub use self::total_curvature::TotalCurvature;
+pub use self::vertical_excess_curvature::VerticalExcessCurvature;
 pub use self::viewshed::Viewshed;
 pub use self::visibility_index::VisibilityIndex;
 pub use self::wetness_index::WetnessIndex;
+
+pub(crate) use self::synthetic_terrain::{valley_elevation, valley_flow_accumulation};
+pub(crate) use self::time_in_daylight::pos as solar_position;
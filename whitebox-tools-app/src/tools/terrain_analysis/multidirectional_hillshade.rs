@@ -8,15 +8,14 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool performs a hillshade operation (also called shaded relief) on an input digital elevation model (DEM)
 /// with multiple sources of illumination. The user must specify the  name of the input DEM (`--dem`) and the output
@@ -77,7 +76,7 @@ impl MultidirectionalHillshade {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -98,7 +97,7 @@ impl MultidirectionalHillshade {
             flags: vec!["--altitude".to_owned()],
             description: "Illumination source altitude in degrees.".to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("45.0".to_owned()),
+            default_value: Some("30.0".to_owned()),
             optional: true,
         });
 
@@ -115,7 +114,7 @@ impl MultidirectionalHillshade {
 
         parameters.push(ToolParameter {
             name: "Full 360-degree mode?".to_owned(),
-            flags: vec!["--full_mode".to_owned()],
+            flags: vec!["--fullmode".to_owned(), "--full_mode".to_owned()],
             description:
                 "Optional flag indicating whether to use full 360-degrees of illumination sources."
                     .to_owned(),
@@ -193,71 +192,14 @@ impl WhiteboxTool for MultidirectionalHillshade {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut altitude = 30.0f64;
-        let mut z_factor = -1f64;
-        let mut multidirection360mode = false;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-altitude" {
-                if keyval {
-                    altitude = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    altitude = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-full_mode" || flag_val == "-fullmode" {
-                if vec.len() == 1 || !vec[1].to_string().to_lowercase().contains("false") {
-                    multidirection360mode = true;
-                }
-            } else if flag_val == "-zfactor" {
-                if keyval {
-                    z_factor = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    z_factor = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut altitude = parsed.get_f64("altitude").unwrap_or(30.0f64);
+        let mut z_factor = parsed.get_f64("zfactor").unwrap_or(-1f64);
+        let multidirection360mode = parsed.get_bool("full_mode");
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -270,17 +212,11 @@ impl WhiteboxTool for MultidirectionalHillshade {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -313,17 +249,13 @@ impl WhiteboxTool for MultidirectionalHillshade {
         let out_nodata = output.configs.nodata;
         let rows = input.configs.rows as isize;
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let nodata = input.configs.nodata;
                 let columns = input.configs.columns as isize;
                 let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
@@ -367,11 +299,11 @@ impl WhiteboxTool for MultidirectionalHillshade {
                     let mut data = vec![out_nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             z = z * z_factor;
                             for c in 0..8 {
                                 n[c] = input.get_value(row + d_y[c], col + d_x[c]);
-                                if n[c] != nodata {
+                                if !is_nodata(n[c], nodata) {
                                     n[c] = n[c] * z_factor;
                                 } else {
                                     n[c] = z;
@@ -405,7 +337,9 @@ impl WhiteboxTool for MultidirectionalHillshade {
                             data[col as usize] = z.round();
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -416,7 +350,7 @@ impl WhiteboxTool for MultidirectionalHillshade {
             let data = rx.recv().expect("Error receiving data from thread.");
             let mut bin: usize;
             for col in 0..data.1.len() {
-                if data.1[col] != out_nodata {
+                if !is_nodata(data.1[col], out_nodata) {
                     bin = data.1[col] as usize;
                     histo[bin] += 1.0;
                     num_cells += 1.0;
@@ -424,10 +358,11 @@ impl WhiteboxTool for MultidirectionalHillshade {
             }
             output.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Performing analysis: {}%", progress);
+                    reporter.progress("Performing analysis", progress);
                     old_progress = progress;
                 }
             }
@@ -9,15 +9,14 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::i64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool calculates the most elevation percentile (EP) across a range of spatial scales.
 /// EP is a measure of local topographic position (LTP) and expresses the vertical
@@ -91,7 +90,7 @@ impl MultiscaleElevationPercentile {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -226,94 +225,21 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_mag_file = String::new();
-        let mut output_scale_file = String::new();
-        let mut num_sig_digits = 3i32;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_mag_file = parsed.get_string("out_mag").unwrap_or_default();
+        let mut output_scale_file = parsed.get_string("out_scale").unwrap_or_default();
+        let num_sig_digits = parsed.get_i64("sig_digits").map(|v| v as i32).unwrap_or(3i32);
         let mut min_scale = 4isize;
-        let mut step = 1isize;
-        let mut num_steps = 10isize;
-        let mut step_nonlinearity = 1.0f32;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_mag" {
-                output_mag_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_scale" {
-                output_scale_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-min_scale" {
-                min_scale = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-                if min_scale < 1 {
-                    min_scale = 1;
-                }
-            } else if flag_val == "-step" {
-                step = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-num_steps" {
-                num_steps = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-step_nonlinearity" {
-                step_nonlinearity = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-sig_digits" {
-                num_sig_digits = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<i32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<i32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
+        let mut step = parsed.get_i64("step").map(|v| v as isize).unwrap_or(1isize);
+        let mut num_steps = parsed.get_i64("num_steps").map(|v| v as isize).unwrap_or(10isize);
+        let mut step_nonlinearity = parsed.get_f64("step_nonlinearity").map(|v| v as f32).unwrap_or(1.0f32);
+        if let Some(value) = parsed.get_i64("min_scale") {
+            min_scale = value as isize;
+            if min_scale < 1 {
+                min_scale = 1;
             }
         }
 
@@ -348,20 +274,12 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_mag_file.contains(&sep) && !output_mag_file.contains("/") {
-            output_mag_file = format!("{}{}", working_directory, output_mag_file);
-        }
-        if !output_scale_file.contains(&sep) && !output_scale_file.contains("/") {
-            output_scale_file = format!("{}{}", working_directory, output_scale_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_mag_file = resolve_path(&output_mag_file, working_directory);
+        output_scale_file = resolve_path(&output_scale_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -382,29 +300,27 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
         let bin_nodata = std::i16::MIN as i64;
         let mut binned_data: Array2D<i64> = Array2D::new(rows, columns, bin_nodata, bin_nodata)?; // Memory requirements: 4.0X
 
-        let mut num_procs = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx1 = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f64;
                 let mut val: i64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
                     let mut data = vec![bin_nodata; columns as usize];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             val = (z * multiplier).floor() as i64 - min_bin;
                             data[col as usize] = val;
                         }
                     }
-                    tx1.send((row, data)).unwrap();
+                    if tx1.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -412,10 +328,11 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             binned_data.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Binning data: {}%", progress);
+                    reporter.progress("Binning data", progress);
                     old_progress = progress;
                 }
             }
@@ -456,7 +373,7 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
             for tid in 0..num_procs {
                 let binned_data = bd.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut bin_val, mut bin_val_n, mut old_bin_val): (i64, i64, i64);
                     let (mut start_col, mut end_col, mut start_row, mut end_row): (
                         isize,
@@ -546,7 +463,9 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
 
                             old_bin_val = bin_val;
                         }
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -556,9 +475,9 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
             for r in 0..rows {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 for col in 0..columns {
-                    if data[col as usize] != nodata {
+                    if !is_nodata(data[col as usize], nodata) {
                         z1 = output_mag.get_value(row, col);
-                        if z1 == nodata {
+                        if is_nodata(z1, nodata) {
                             output_mag.set_value(row, col, data[col as usize]);
                             output_scale.set_value(row, col, s as f64);
                         } else {
@@ -571,19 +490,21 @@ impl WhiteboxTool for MultiscaleElevationPercentile {
                         }
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Performing analysis: {}%", progress);
+                        reporter.progress("Performing analysis", progress);
                         old_progress = progress;
                     }
                 }
             }
             // Update progress
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (s as f32 / num_steps as f32 * 100f32) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
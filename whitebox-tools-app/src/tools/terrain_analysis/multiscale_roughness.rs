@@ -9,16 +9,15 @@ License: MIT
 use whitebox_raster::*;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::ops::AddAssign;
 use std::ops::SubAssign;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 pub struct MultiscaleRoughness {
     name: String,
@@ -39,7 +38,7 @@ impl MultiscaleRoughness {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -156,68 +155,19 @@ impl WhiteboxTool for MultiscaleRoughness {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_mag_file = String::new();
-        let mut output_scale_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_mag_file = parsed.get_string("out_mag").unwrap_or_default();
+        let mut output_scale_file = parsed.get_string("out_scale").unwrap_or_default();
         let mut min_scale = 1isize;
-        let mut max_scale = 100isize;
-        let mut step = 1isize;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_mag" {
-                output_mag_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_scale" {
-                output_scale_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-min_scale" {
-                min_scale = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-                if min_scale < 1 {
-                    min_scale = 1;
-                }
-            } else if flag_val == "-max_scale" {
-                max_scale = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-step" {
-                step = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
+        let mut max_scale = parsed.get_i64("max_scale").map(|v| v as isize).unwrap_or(100isize);
+        let mut step = parsed.get_i64("step").map(|v| v as isize).unwrap_or(1isize);
+        if let Some(value) = parsed.get_i64("min_scale") {
+            min_scale = value as isize;
+            if min_scale < 1 {
+                min_scale = 1;
             }
         }
 
@@ -246,20 +196,12 @@ impl WhiteboxTool for MultiscaleRoughness {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_mag_file.contains(&sep) && !output_mag_file.contains("/") {
-            output_mag_file = format!("{}{}", working_directory, output_mag_file);
-        }
-        if !output_scale_file.contains(&sep) && !output_scale_file.contains("/") {
-            output_scale_file = format!("{}{}", working_directory, output_scale_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_mag_file = resolve_path(&output_mag_file, working_directory);
+        output_scale_file = resolve_path(&output_scale_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -284,17 +226,13 @@ impl WhiteboxTool for MultiscaleRoughness {
         ///////////////////////////////
         // Create the normal vectors //
         ///////////////////////////////
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                 let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                 let eight_grid_res = input.configs.resolution_x * 8f64;
@@ -313,11 +251,11 @@ impl WhiteboxTool for MultiscaleRoughness {
                     let mut values = [0f64; 9];
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             z *= z_factor;
                             for i in 0..8 {
                                 zn = input.get_value(row + dy[i], col + dx[i]);
-                                if zn != nodata {
+                                if !is_nodata(zn, nodata) {
                                     values[i] = zn * z_factor;
                                 } else {
                                     values[i] = z;
@@ -338,7 +276,9 @@ impl WhiteboxTool for MultiscaleRoughness {
                             };
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -353,10 +293,11 @@ impl WhiteboxTool for MultiscaleRoughness {
             let data = rx.recv().expect("Error receiving data from thread.");
             nv.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Calculating normal vectors: {}%", progress);
+                    reporter.progress("Calculating normal vectors", progress);
                     old_progress = progress;
                 }
             }
@@ -378,7 +319,7 @@ impl WhiteboxTool for MultiscaleRoughness {
             sum_n = 0;
             for col in 0..columns {
                 val = input[(row, col)];
-                if val == nodata {
+                if is_nodata(val, nodata) {
                     val = 0f64;
                 } else {
                     sum_n += 1;
@@ -394,10 +335,11 @@ impl WhiteboxTool for MultiscaleRoughness {
                     integral_n[(row, col)] = sum_n;
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Creating integral images: {}%", progress);
+                    reporter.progress("Creating integral images", progress);
                     old_progress = progress;
                 }
             }
@@ -406,12 +348,8 @@ impl WhiteboxTool for MultiscaleRoughness {
         let i = Arc::new(integral); // wrap integral in an Arc
         let i_n = Arc::new(integral_n); // wrap integral_n in an Arc
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         let mut output_mag = Raster::initialize_using_file(&output_mag_file, &input);
         output_mag.configs.data_type = DataType::F32;
@@ -435,7 +373,7 @@ impl WhiteboxTool for MultiscaleRoughness {
                 let i = i.clone();
                 let i_n = i_n.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
                     let mut n: i32;
                     let mut sum: f64;
@@ -453,7 +391,7 @@ impl WhiteboxTool for MultiscaleRoughness {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input_data[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -471,7 +409,9 @@ impl WhiteboxTool for MultiscaleRoughness {
                             }
                         }
 
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -498,11 +438,11 @@ impl WhiteboxTool for MultiscaleRoughness {
             for row in 0..rows {
                 sum = 0f64;
                 for col in 0..columns {
-                    if input.get_value(row, col) != nodata {
+                    if !is_nodata(input.get_value(row, col), nodata) {
                         z = smoothed.get_value(row, col) * z_factor;
                         for i in 0..8 {
                             zn = smoothed.get_value(row + dy[i], col + dx[i]);
-                            if zn != nodata {
+                            if !is_nodata(zn, nodata) {
                                 values[i] = zn * z_factor;
                             } else {
                                 values[i] = z;
@@ -546,7 +486,7 @@ impl WhiteboxTool for MultiscaleRoughness {
                 let i_n = i_n.clone();
                 let i_diff_nv = i_diff_nv.clone();
                 let tx1 = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
                     let mut n: i32;
                     let mut sum: f64;
@@ -564,7 +504,7 @@ impl WhiteboxTool for MultiscaleRoughness {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input_data[(row, col)];
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -584,7 +524,9 @@ impl WhiteboxTool for MultiscaleRoughness {
                             }
                         }
 
-                        tx1.send((row, data)).unwrap();
+                        if tx1.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
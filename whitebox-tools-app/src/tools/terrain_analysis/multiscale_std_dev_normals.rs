@@ -11,7 +11,6 @@ use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs::File;
@@ -22,7 +21,7 @@ use std::path::Path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to map the spatial pattern of maximum spherical standard deviation
 /// (σ<sub>*s max*</sub>; `--out_mag`), as well as the scale at which maximum spherical standard deviation occurs
@@ -77,7 +76,7 @@ impl MultiscaleStdDevNormals {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -203,81 +202,20 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_mag_file = String::new();
-        let mut output_scale_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_mag_file = parsed.get_string("out_mag").unwrap_or_default();
+        let mut output_scale_file = parsed.get_string("out_scale").unwrap_or_default();
         let mut min_scale = 1isize;
-        let mut step = 1isize;
-        let mut num_steps = 10isize;
-        let mut step_nonlinearity = 1.0f32;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_mag" {
-                output_mag_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-out_scale" {
-                output_scale_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-min_scale" {
-                min_scale = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-                if min_scale < 1 {
-                    min_scale = 1;
-                }
-            } else if flag_val == "-step" {
-                step = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-num_steps" {
-                num_steps = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-step_nonlinearity" {
-                step_nonlinearity = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
+        let mut step = parsed.get_i64("step").map(|v| v as isize).unwrap_or(1isize);
+        let mut num_steps = parsed.get_i64("num_steps").map(|v| v as isize).unwrap_or(10isize);
+        let mut step_nonlinearity = parsed.get_f64("step_nonlinearity").map(|v| v as f32).unwrap_or(1.0f32);
+        if let Some(value) = parsed.get_i64("min_scale") {
+            min_scale = value as isize;
+            if min_scale < 1 {
+                min_scale = 1;
             }
         }
 
@@ -322,20 +260,12 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_mag_file.contains(&sep) && !output_mag_file.contains("/") {
-            output_mag_file = format!("{}{}", working_directory, output_mag_file);
-        }
-        if !output_scale_file.contains(&sep) && !output_scale_file.contains("/") {
-            output_scale_file = format!("{}{}", working_directory, output_scale_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_mag_file = resolve_path(&output_mag_file, working_directory);
+        output_scale_file = resolve_path(&output_scale_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -364,12 +294,8 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
             }
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
 
         if verbose {
             println!("Initializing grids...");
@@ -383,10 +309,11 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
         let mut val: u32;
         let mut num_valid_cells = 0u64;
         for row in 0..rows {
+            reporter.check_cancelled()?;
             if row > 0 {
                 sum = 0u32;
                 for col in 0..columns {
-                    sum += if input.get_value(row, col) != nodata {
+                    sum += if !is_nodata(input.get_value(row, col) as f64, nodata as f64) {
                         input.set_value(row, col, input.get_value(row, col) - min_val);
                         num_valid_cells += 1;
                         1
@@ -397,14 +324,14 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                     i_n.set_value(row, col, sum + i_n.get_value(row - 1, col));
                 }
             } else {
-                if input.get_value(0, 0) != nodata {
+                if !is_nodata(input.get_value(0, 0) as f64, nodata as f64) {
                     i_n.set_value(0, 0, 1);
                     num_valid_cells += 1;
                 } else {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
-                    val = if input.get_value(row, col) != nodata {
+                    val = if !is_nodata(input.get_value(row, col) as f64, nodata as f64) {
                         input.set_value(row, col, input.get_value(row, col) - min_val);
                         num_valid_cells += 1;
                         1
@@ -508,7 +435,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                     let d_y = d_y.clone();
                     let weights = weights.clone();
                     let tx1 = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         let (mut sum, mut z_final): (f32, f32);
                         let mut z: f32;
                         let mut zn: f32;
@@ -517,14 +444,14 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 z = input.get_value(row, col);
-                                if z != nodata {
+                                if !is_nodata(z as f64, nodata as f64) {
                                     sum = 0.0;
                                     z_final = 0.0;
                                     for a in 0..num_pixels_in_filter {
                                         x = col + d_x[a];
                                         y = row + d_y[a];
                                         zn = input.get_value(y, x);
-                                        if zn != nodata {
+                                        if !is_nodata(zn as f64, nodata as f64) {
                                             sum += weights[a];
                                             z_final += weights[a] * zn;
                                         }
@@ -533,7 +460,9 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                                 }
                             }
 
-                            tx1.send((row, data)).unwrap();
+                            if tx1.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -581,7 +510,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                             // sum_n = 0;
                             for col in 0..columns {
                                 val = input.get_value(row, col);
-                                if val == nodata {
+                                if is_nodata(val as f64, nodata as f64) {
                                     val = 0f32;
                                     // } else {
                                     // sum_n += 1;
@@ -602,7 +531,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                             sum = 0f64;
                             for col in 0..columns {
                                 val = smoothed_dem.get_value(row, col);
-                                if val == nodata {
+                                if is_nodata(val as f64, nodata as f64) {
                                     val = 0f32;
                                 }
                                 sum += val as f64;
@@ -628,7 +557,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                         }
 
                         for col in 0..columns {
-                            if input.get_value(row, col) != nodata {
+                            if !is_nodata(input.get_value(row, col) as f64, nodata as f64) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -671,7 +600,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
             for tid in 0..num_procs {
                 let smoothed_dem = smoothed_dem.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                     let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                     let mut n: [f32; 8] = [0.0; 8];
@@ -688,10 +617,10 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                         let mut zdata = vec![0f64; columns as usize];
                         for col in 0..columns {
                             z = smoothed_dem.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z as f64, nodata as f64) {
                                 for c in 0..8 {
                                     n[c] = smoothed_dem.get_value(row + dy[c], col + dx[c]);
-                                    if n[c] != nodata {
+                                    if !is_nodata(n[c] as f64, nodata as f64) {
                                         n[c] = n[c] * z_factor;
                                     } else {
                                         n[c] = z * z_factor;
@@ -711,7 +640,9 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                                 }
                             }
                         }
-                        tx.send((row, xdata, ydata, zdata)).unwrap();
+                        if tx.send((row, xdata, ydata, zdata)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -769,7 +700,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                 let input = input.clone();
                 let i_n = i_n.clone();
                 let tx2 = tx2.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut x1, mut x2, mut y1, mut y2): (isize, isize, isize, isize);
                     let mut n: f32;
                     let (mut sumx, mut sumy, mut sumz): (f64, f64, f64);
@@ -794,7 +725,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                         let mut data = vec![nodata; columns as usize];
                         for col in 0..columns {
                             z = input.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z as f64, nodata as f64) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -835,14 +766,8 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                             }
                         }
 
-                        match tx2.send((row, data)) {
-                            Ok(_) => {}
-                            Err(_) => {
-                                println!(
-                                    "Error sending data from thread {} processing row {}.",
-                                    tid, row
-                                );
-                            }
+                        if tx2.send((row, data)).is_err() {
+                            return;
                         }
                     }
                 });
@@ -853,7 +778,7 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
                 match rx2.recv() {
                     Ok((row, data)) => {
                         for col in 0..columns {
-                            if data[col as usize] != nodata {
+                            if !is_nodata(data[col as usize] as f64, nodata as f64) {
                                 total_mssd += data[col as usize] as f64;
                                 if data[col as usize] > output_mag.get_value(row, col) {
                                     output_mag.set_value(row, col, data[col as usize]);
@@ -882,10 +807,11 @@ impl WhiteboxTool for MultiscaleStdDevNormals {
             // drop(zc); // Memory requirements: 3.5X
 
             // Update progress
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (s as f32 / num_steps as f32 * 100f32) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
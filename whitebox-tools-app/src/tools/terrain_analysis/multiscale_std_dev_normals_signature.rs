@@ -6,13 +6,12 @@ Last Modified: 03/09/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs::File;
@@ -23,7 +22,7 @@ use std::path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 pub struct MultiscaleStdDevNormalsSignature {
     name: String,
@@ -45,7 +44,7 @@ impl MultiscaleStdDevNormalsSignature {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -173,81 +172,20 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut points_file = String::new();
-        let mut output_file = String::new();
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut points_file = parsed.get_string("points").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
         let mut min_scale = 1isize;
-        let mut step = 1isize;
-        let mut num_steps = 10isize;
-        let mut step_nonlinearity = 1.0f32;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-points" {
-                points_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-min_scale" {
-                min_scale = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-                if min_scale < 1 {
-                    min_scale = 1;
-                }
-            } else if flag_val == "-step" {
-                step = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-num_steps" {
-                num_steps = if keyval {
-                    vec[1].to_string().parse::<isize>().unwrap()
-                } else {
-                    args[i + 1].to_string().parse::<isize>().unwrap()
-                };
-            } else if flag_val == "-step_nonlinearity" {
-                step_nonlinearity = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
+        let mut step = parsed.get_i64("step").map(|v| v as isize).unwrap_or(1isize);
+        let mut num_steps = parsed.get_i64("num_steps").map(|v| v as isize).unwrap_or(10isize);
+        let mut step_nonlinearity = parsed.get_f64("step_nonlinearity").map(|v| v as f32).unwrap_or(1.0f32);
+        if let Some(value) = parsed.get_i64("min_scale") {
+            min_scale = value as isize;
+            if min_scale < 1 {
+                min_scale = 1;
             }
         }
 
@@ -282,20 +220,12 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !points_file.contains(&sep) && !points_file.contains("/") {
-            points_file = format!("{}{}", working_directory, points_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        points_file = resolve_input_path(&points_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading DEM data...")
@@ -353,10 +283,11 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                 series_names.push(format!("Site {}", record_num + 1));
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Finding site row/column values: {}%", progress);
+                    reporter.progress("Finding site row/column values", progress);
                     old_progress = progress;
                 }
             }
@@ -371,10 +302,11 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
         let mut sum: u32;
         let mut val: u32;
         for row in 0..rows {
+            reporter.check_cancelled()?;
             if row > 0 {
                 sum = 0u32;
                 for col in 0..columns {
-                    sum += if input.get_value(row, col) != nodata {
+                    sum += if !is_nodata(input.get_value(row, col) as f64, nodata as f64) {
                         input.set_value(row, col, input.get_value(row, col) - min_val);
                         1
                     } else {
@@ -384,13 +316,13 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                     i_n.set_value(row, col, sum + i_n.get_value(row - 1, col));
                 }
             } else {
-                if input.get_value(0, 0) != nodata {
+                if !is_nodata(input.get_value(0, 0) as f64, nodata as f64) {
                     i_n.set_value(0, 0, 1);
                 } else {
                     i_n.set_value(0, 0, 0);
                 }
                 for col in 1..columns {
-                    val = if input.get_value(row, col) != nodata {
+                    val = if !is_nodata(input.get_value(row, col) as f64, nodata as f64) {
                         input.set_value(row, col, input.get_value(row, col) - min_val);
                         1
                     } else {
@@ -408,12 +340,8 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
         ///////////////////////////////
         // Perform the main analysis //
         ///////////////////////////////
-        let mut num_procs = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
         for s in min_scale..(min_scale + num_steps) {
             let midpoint = min_scale
                 + ((((s - min_scale) * step) as f32).powf(step_nonlinearity)).floor() as isize;
@@ -486,7 +414,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                     let d_y = d_y.clone();
                     let weights = weights.clone();
                     let tx1 = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         let (mut sum, mut z_final): (f32, f32);
                         let mut z: f32;
                         let mut zn: f32;
@@ -495,14 +423,14 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 z = input.get_value(row, col);
-                                if z != nodata {
+                                if !is_nodata(z as f64, nodata as f64) {
                                     sum = 0.0;
                                     z_final = 0.0;
                                     for a in 0..num_pixels_in_filter {
                                         x = col + d_x[a];
                                         y = row + d_y[a];
                                         zn = input.get_value(y, x);
-                                        if zn != nodata {
+                                        if !is_nodata(zn as f64, nodata as f64) {
                                             sum += weights[a];
                                             z_final += weights[a] * zn;
                                         }
@@ -511,7 +439,9 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                                 }
                             }
 
-                            tx1.send((row, data)).unwrap();
+                            if tx1.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -559,7 +489,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                             // sum_n = 0;
                             for col in 0..columns {
                                 val = input.get_value(row, col);
-                                if val == nodata {
+                                if is_nodata(val as f64, nodata as f64) {
                                     val = 0f32;
                                     // } else {
                                     // sum_n += 1;
@@ -580,7 +510,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                             sum = 0f64;
                             for col in 0..columns {
                                 val = smoothed_dem.get_value(row, col);
-                                if val == nodata {
+                                if is_nodata(val as f64, nodata as f64) {
                                     val = 0f32;
                                 }
                                 sum += val as f64;
@@ -606,7 +536,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                         }
 
                         for col in 0..columns {
-                            if input.get_value(row, col) != nodata {
+                            if !is_nodata(input.get_value(row, col) as f64, nodata as f64) {
                                 x1 = col - midpoint - 1;
                                 if x1 < 0 {
                                     x1 = 0;
@@ -649,7 +579,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
             for tid in 0..num_procs {
                 let smoothed_dem = smoothed_dem.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let dx = [1, 1, 1, 0, -1, -1, -1, 0];
                     let dy = [-1, 0, 1, 1, 1, 0, -1, -1];
                     let mut n: [f32; 8] = [0.0; 8];
@@ -666,10 +596,10 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                         let mut zdata = vec![0f64; columns as usize];
                         for col in 0..columns {
                             z = smoothed_dem.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z as f64, nodata as f64) {
                                 for c in 0..8 {
                                     n[c] = smoothed_dem.get_value(row + dy[c], col + dx[c]);
-                                    if n[c] != nodata {
+                                    if !is_nodata(n[c] as f64, nodata as f64) {
                                         n[c] = n[c] * z_factor;
                                     } else {
                                         n[c] = z * z_factor;
@@ -689,7 +619,9 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
                                 }
                             }
                         }
-                        tx.send((row, xdata, ydata, zdata)).unwrap();
+                        if tx.send((row, xdata, ydata, zdata)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -744,7 +676,7 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
             for site in 0..signature_sites.len() {
                 let (row, col) = signature_sites[site];
                 z = input.get_value(row, col);
-                if z != nodata {
+                if !is_nodata(z as f64, nodata as f64) {
                     y1 = row - midpoint - 1;
                     if y1 < 0 {
                         y1 = 0;
@@ -793,10 +725,11 @@ impl WhiteboxTool for MultiscaleStdDevNormalsSignature {
             // drop(zc); // Memory requirements: 3.5X
 
             // Update progress
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (s as f32 / num_steps as f32 * 100f32) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool creates a multiscale topographic position (MTP) image ([see here for an
 /// example](https://www.uoguelph.ca/~hydrogeo/pubs/UpdatedPosterMapSm.png)) from three DEV<sub>max</sub> rasters of differing
@@ -166,66 +165,14 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input1_file = String::new();
-        let mut input2_file = String::new();
-        let mut input3_file = String::new();
-        let mut output_file = String::new();
-        let mut cutoff = 1.2f64;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-broad" {
-                if keyval {
-                    input1_file = vec[1].to_string();
-                } else {
-                    input1_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-meso" {
-                if keyval {
-                    input2_file = vec[1].to_string();
-                } else {
-                    input2_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-local" {
-                if keyval {
-                    input3_file = vec[1].to_string();
-                } else {
-                    input3_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-lightness" {
-                if keyval {
-                    cutoff = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    cutoff = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input1_file = parsed.get_string("broad").unwrap_or_default();
+        let mut input2_file = parsed.get_string("meso").unwrap_or_default();
+        let mut input3_file = parsed.get_string("local").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let cutoff = parsed.get_f64("lightness").unwrap_or(1.2f64);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -238,23 +185,13 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input1_file.contains(&sep) && input1_file.contains("/") {
-            input1_file = format!("{}{}", working_directory, input1_file);
-        }
-        if !input2_file.contains(&sep) && input2_file.contains("/") {
-            input2_file = format!("{}{}", working_directory, input2_file);
-        }
-        if !input3_file.contains(&sep) && input3_file.contains("/") {
-            input3_file = format!("{}{}", working_directory, input3_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input1_file = resolve_input_path(&input1_file, working_directory)?;
+        input2_file = resolve_input_path(&input2_file, working_directory)?;
+        input3_file = resolve_input_path(&input3_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading broad-scale DEV data...")
@@ -287,33 +224,25 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
         if input_r.configs.rows != input_g.configs.rows
             || input_r.configs.columns != input_g.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input_r, &input_g, verbose);
         if input_r.configs.rows != input_b.configs.rows
             || input_r.configs.columns != input_b.configs.columns
         {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&input_r, &input_b, verbose);
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input_r = input_r.clone();
             let input_g = input_g.clone();
             let input_b = input_b.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut red_val: f64;
                 let mut green_val: f64;
                 let mut blue_val: f64;
@@ -324,7 +253,7 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
                         red_val = input_r[(row, col)];
                         green_val = input_g[(row, col)];
                         blue_val = input_b[(row, col)];
-                        if red_val != nodata_r && green_val != nodata_g && blue_val != nodata_b {
+                        if !is_nodata(red_val, nodata_r) && !is_nodata(green_val, nodata_g) && !is_nodata(blue_val, nodata_b) {
                             /* Replaced the linear interpolation with this logistic function.*/
                             red_val = (512f64 / (1f64 + (-cutoff * (red_val).abs()).exp())).floor()
                                 - 256f64;
@@ -363,7 +292,9 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
                                 ((255 << 24) | (blue << 16) | (green << 8) | red) as f64;
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -374,10 +305,11 @@ impl WhiteboxTool for MultiscaleTopographicPositionImage {
         for row in 0..rows {
             let data = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(data.0, data.1);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
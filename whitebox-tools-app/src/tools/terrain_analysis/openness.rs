@@ -0,0 +1,285 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::horizon_search::{ground_cell_sizes, horizon_search};
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::f64::consts::FRAC_PI_2;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+
+/// This tool calculates the positive and negative topographic openness (Yokoyama et al., 2002) of each grid
+/// cell in an input digital elevation model (`--dem`). Openness is an angular measure of the relation between
+/// surface relief and horizontal distance. For each of a number of equally spaced azimuths (`--num_directions`),
+/// a ray is traced outward from the cell to a maximum search distance (`--max_dist`), and the largest and
+/// smallest elevation angles between the cell and the terrain along the ray are found. Positive openness
+/// (`--pos_output`) is the mean, over all directions, of the zenith angle of the horizon, i.e. 90 degrees minus
+/// the largest elevation angle, and negative openness (`--neg_output`) is the mean nadir angle of the horizon,
+/// i.e. 90 degrees plus the smallest elevation angle. At least one of the two outputs must be specified, and both
+/// are calculated in a single pass when both are specified. The outputs are measured in degrees.
+///
+/// Positive openness is high on convex landforms, such as ridges, crests, and banks, and low in concave landforms;
+/// negative openness is the converse, highlighting valleys, ditches, and other depressions. Unlike hillshading,
+/// openness is not dependent on an illumination direction, and for this reason it is widely used for the
+/// visualization of subtle archaeological and geomorphological features in high-resolution DEMs. Openness is
+/// scale dependent: small search distances highlight small features, while larger distances emphasize the
+/// broader landscape. The search distance is measured in the horizontal units of the DEM (or metres for DEMs in
+/// geographic coordinates) and defaults to ten grid cells.
+///
+/// Rays that leave the DEM are truncated at its edge; openness values near the edges of the DEM, and near
+/// areas of nodata, are therefore based on less information than elsewhere. The horizon search is shared
+/// with the `SkyViewFactor` tool.
+///
+/// # Reference
+/// Yokoyama, R., Shirasawa, M., and Pike, R. J. (2002). Visualizing topography by openness: a new application
+/// of image processing to digital elevation models. *Photogrammetric Engineering and Remote Sensing*, 68(3),
+/// 257-266.
+///
+/// # See Also
+/// `SkyViewFactor`, `LocalDominance`, `HorizonAngle`, `Hillshade`, `MultidirectionalHillshade`
+pub struct Openness {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl Openness {
+    pub fn new() -> Openness {
+        // public constructor
+        let name = "Openness".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Calculates the positive and negative topographic openness of a DEM.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Positive Openness File (optional)".to_owned(),
+            flags: vec!["--pos_output".to_owned()],
+            description: "Output positive openness raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Negative Openness File (optional)".to_owned(),
+            flags: vec!["--neg_output".to_owned()],
+            description: "Output negative openness raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Search Distance (optional)".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Maximum search distance, in map units; defaults to ten grid cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Search Directions".to_owned(),
+            flags: vec!["--num_directions".to_owned()],
+            description: "Number of equally spaced search directions.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("16".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --pos_output=pos_openness.tif --neg_output=neg_openness.tif --max_dist=25.0 --num_directions=16",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        Openness {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for Openness {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut pos_output_file = parsed.get_string("pos_output").unwrap_or_default();
+        let mut neg_output_file = parsed.get_string("neg_output").unwrap_or_default();
+        let mut max_dist = parsed.get_f64("max_dist").unwrap_or(0f64);
+        let mut num_directions = parsed.get_usize("num_directions")?.unwrap_or(16usize);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        if pos_output_file.is_empty() && neg_output_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one of the positive (--pos_output) and negative (--neg_output) openness outputs must be specified.",
+            ));
+        }
+        pos_output_file = resolve_path(&pos_output_file, working_directory);
+        neg_output_file = resolve_path(&neg_output_file, working_directory);
+        if num_directions < 4 {
+            num_directions = 4;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let (cell_size_x, cell_size_y) = ground_cell_sizes(&input);
+        if max_dist <= 0f64 {
+            max_dist = 10f64 * cell_size_x.max(cell_size_y);
+        }
+        if max_dist < cell_size_x.max(cell_size_y) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum search distance (--max_dist) must be at least one grid cell.",
+            ));
+        }
+
+        let outputs = horizon_search(
+            &input,
+            num_directions,
+            max_dist,
+            2,
+            move |max_angles, min_angles, values| {
+                let n = max_angles.len() as f64;
+                values[0] = (FRAC_PI_2 - max_angles.iter().sum::<f64>() / n).to_degrees();
+                values[1] = (FRAC_PI_2 + min_angles.iter().sum::<f64>() / n).to_degrees();
+            },
+            verbose,
+            reporter,
+        )?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+
+        for (output_file, data, label) in [
+            (&pos_output_file, &outputs[0], "positive"),
+            (&neg_output_file, &outputs[1], "negative"),
+        ] {
+            if output_file.is_empty() {
+                continue;
+            }
+            let mut output = Raster::initialize_using_file(output_file, &input);
+            output.configs.data_type = DataType::F32;
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.palette = "grey.plt".to_string();
+            output.configs.z_units = "degrees".to_string();
+            for row in 0..data.rows() {
+                output.set_row_data(row, data.get_row_data(row));
+            }
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("Input file: {}", input_file));
+            output.add_metadata_entry(format!("Openness: {}", label));
+            output.add_metadata_entry(format!("Maximum search distance: {}", max_dist));
+            output.add_metadata_entry(format!("Number of directions: {}", num_directions));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
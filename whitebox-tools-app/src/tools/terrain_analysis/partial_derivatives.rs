@@ -0,0 +1,433 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+// The partial derivative engine shared by the curvature tools. Each tool supplies a function
+// of the partial derivatives; the polynomial fitting is done here, once.
+
+use crate::tools::ProgressReporter;
+use whitebox_raster::{is_nodata, Raster};
+use std::f64;
+use std::io::Error;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+use whitebox_common::utils::{haversine_distance, vincenty_distance};
+
+/// The first- (p, q) and second-order (r, s, t) partial derivatives of elevation at a grid cell,
+/// using the notation of Florinsky (2016).
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct PartialDerivatives {
+    pub p: f64,
+    pub q: f64,
+    pub r: f64,
+    pub s: f64,
+    pub t: f64,
+}
+
+/*
+The following equations have been taken from Florinsky (2016) Principles and Methods
+of Digital Terrain Modelling, Chapter 2, pg. 18-21, and Florinsky (2017) An illustrated
+introduction to general geomorphometry. Curvatures involving the direction of the gradient
+are zero where the gradient vanishes.
+*/
+impl PartialDerivatives {
+    fn grad_sqrd(&self) -> f64 {
+        self.p * self.p + self.q * self.q
+    }
+
+    pub fn mean_curvature(&self) -> f64 {
+        let (p, q, r, s, t) = (self.p, self.q, self.r, self.s, self.t);
+        -((1. + q * q) * r - 2. * p * q * s + (1. + p * p) * t) / (2. * ((1. + p * p + q * q).powi(3)).sqrt())
+    }
+
+    pub fn gaussian_curvature(&self) -> f64 {
+        let (p, q, r, s, t) = (self.p, self.q, self.r, self.s, self.t);
+        (r * t - s * s) / (1. + p * p + q * q).powi(2)
+    }
+
+    pub fn unsphericity(&self) -> f64 {
+        let h = self.mean_curvature();
+        (h * h - self.gaussian_curvature()).max(0.).sqrt()
+    }
+
+    pub fn minimal_curvature(&self) -> f64 {
+        self.mean_curvature() - self.unsphericity()
+    }
+
+    pub fn maximal_curvature(&self) -> f64 {
+        self.mean_curvature() + self.unsphericity()
+    }
+
+    pub fn plan_curvature(&self) -> f64 {
+        let (p, q, r, s, t) = (self.p, self.q, self.r, self.s, self.t);
+        if (p + q).abs() > 0. {
+            -(q * q * r - 2. * p * q * s + p * p * t) / ((p * p + q * q).powi(3).sqrt())
+        } else {
+            0.
+        }
+    }
+
+    /// Also known as the horizontal curvature, k<sub>h</sub>.
+    pub fn tangential_curvature(&self) -> f64 {
+        let (p, q, r, s, t) = (self.p, self.q, self.r, self.s, self.t);
+        if (p + q).abs() > 0. {
+            -(q * q * r - 2. * p * q * s + p * p * t) / ((p * p + q * q) * (1. + p * p + q * q).sqrt())
+        } else {
+            0.
+        }
+    }
+
+    /// Also known as the vertical curvature, k<sub>v</sub>.
+    pub fn profile_curvature(&self) -> f64 {
+        let (p, q, r, s, t) = (self.p, self.q, self.r, self.s, self.t);
+        if (p + q).abs() > 0. {
+            -(p * p * r + 2. * p * q * s + q * q * t) / ((p * p + q * q) * ((1. + p * p + q * q).powi(3)).sqrt())
+        } else {
+            0.
+        }
+    }
+
+    /// Taken from Wilson (2018) Environmental Applications of Digital Terrain Modelling, eq. 3.15.
+    pub fn total_curvature(&self) -> f64 {
+        self.r * self.r + 2. * self.s * self.s + self.t * self.t
+    }
+
+    pub fn difference_curvature(&self) -> f64 {
+        (self.profile_curvature() - self.tangential_curvature()) / 2.
+    }
+
+    pub fn horizontal_excess_curvature(&self) -> f64 {
+        self.unsphericity() - self.difference_curvature()
+    }
+
+    pub fn vertical_excess_curvature(&self) -> f64 {
+        self.unsphericity() + self.difference_curvature()
+    }
+
+    pub fn accumulation_curvature(&self) -> f64 {
+        self.tangential_curvature() * self.profile_curvature()
+    }
+
+    pub fn rotor(&self) -> f64 {
+        let (p, q, r, s, t) = (self.p, self.q, self.r, self.s, self.t);
+        let g2 = self.grad_sqrd();
+        if g2 > 0. {
+            ((p * p - q * q) * s - p * q * (r - t)) / g2.powf(1.5)
+        } else {
+            0.
+        }
+    }
+
+    pub fn ring_curvature(&self) -> f64 {
+        let (p, q, r, s, t) = (self.p, self.q, self.r, self.s, self.t);
+        let g2 = self.grad_sqrd();
+        if g2 > 0. {
+            ((p * p - q * q) * s - p * q * (r - t)).powi(2) / (g2 * (1. + g2)).powi(2)
+        } else {
+            0.
+        }
+    }
+}
+
+/// Returns the multiplier used in the log-transformation of curvature values, which depends on the
+/// grid resolution in metres (Florinsky, 2016, pg. 246).
+pub(super) fn log_multiplier(res: f64) -> f64 {
+    match res {
+        x if x >= 0. && x < 1. => { 10f64.powi(2) },
+        x if x >= 1. && x < 10. => { 10f64.powi(3) },
+        x if x >= 10. && x < 100. => { 10f64.powi(4) },
+        x if x >= 100. && x < 1000. => { 10f64.powi(5) },
+        x if x >= 1000. && x < 5000. => { 10f64.powi(6) },
+        x if x >= 5000. && x < 10000. => { 10f64.powi(7) },
+        x if x >= 10000. && x < 75000. => { 10f64.powi(8) },
+        _ => { 10f64.powi(9) },
+    }
+}
+
+/// Calculates a curvature (or any other function of the partial derivatives of elevation) for each
+/// grid cell in `input`, writing the results to `output`.
+///
+/// For DEMs in projected coordinate systems, the partial derivatives are estimated using the 3rd-order
+/// bivariate Taylor polynomial fitted to the 5x5 neighbourhood of each cell (Florinsky, 2016). For DEMs
+/// in geographic coordinates, the 3x3 polynomial fitting method for equal angle grids is used instead.
+/// Elevations are multiplied by `z_factor`, and when `log_transform` is true, values are transformed
+/// using the equation of Shary et al. (2002).
+pub(super) fn calculate_curvature<F>(
+    input: &Arc<Raster>,
+    output: &mut Raster,
+    z_factor: f64,
+    log_transform: bool,
+    curvature: F,
+    verbose: bool,
+    reporter: &dyn ProgressReporter,
+) -> Result<(), Error>
+where
+    F: Fn(&PartialDerivatives) -> f64 + Send + Sync + 'static,
+{
+    let rows = input.configs.rows as isize;
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+    let resx = input.configs.resolution_x;
+    let resy = input.configs.resolution_y;
+    let res = (resx + resy) / 2.;
+    let curvature = Arc::new(curvature);
+
+    let configs = whitebox_common::configs::get_configs()?;
+    let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+    let (tx, rx) = mpsc::channel();
+    if !input.is_in_geographic_coordinates() {
+        let log_multiplier = log_multiplier(res);
+
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let curvature = curvature.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let mut z12: f64;
+                let mut p: f64;
+                let mut q: f64;
+                let mut r: f64;
+                let mut s: f64;
+                let mut t: f64;
+                let mut value: f64;
+                let offsets = [
+                    [-2, -2], [-1, -2], [0, -2], [1, -2], [2, -2], 
+                    [-2, -1], [-1, -1], [0, -1], [1, -1], [2, -1], 
+                    [-2, 0], [-1, 0], [0, 0], [1, 0], [2, 0], 
+                    [-2, 1], [-1, 1], [0, 1], [1, 1], [2, 1], 
+                    [-2, 2], [-1, 2], [0, 2], [1, 2], [2, 2]
+                ];
+                let mut z = [0f64; 25];
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z12 = input.get_value(row, col);
+                        if !is_nodata(z12, nodata) {
+                            for n in 0..25 {
+                                z[n] = input.get_value(row + offsets[n][0], col + offsets[n][1]);
+                                if !is_nodata(z[n], nodata) {
+                                    z[n] *= z_factor;
+                                } else {
+                                    z[n] = z12 * z_factor;
+                                }
+                            }
+
+                            /* 
+                            The following equations have been taken from Florinsky (2016) Principles and Methods
+                            of Digital Terrain Modelling, Chapter 4, pg. 117. Note that I believe Florinsky reversed
+                            the equations for q and p.
+                            */
+                            r = 1f64 / (35f64 * res * res) * (2. * (z[0] + z[4] + z[5] + z[9] + z[10] + z[14] + z[15] + z[19] + z[20] + z[24])
+                            - 2. * (z[2] + z[7] + z[12] + z[17] + z[22]) - z[1] - z[3] - z[6] - z[8]
+                            - z[11] - z[13] - z[16] - z[18] - z[21] - z[23]);
+
+                            t = 1f64 / (35f64 * res * res) * (2. * (z[0] + z[1] + z[2] + z[3] + z[4] + z[20] + z[21] + z[22] + z[23] + z[24])
+                            - 2. * (z[10] + z[11] + z[12] + z[13] + z[14]) - z[5] - z[6] - z[7] - z[8]
+                            - z[9] - z[15] - z[16] - z[17] - z[18] - z[19]);
+
+                            s = 1. / (100. * res * res) * (z[8] + z[16] - z[6] - z[18] + 4. * (z[4] + z[20] - z[0] - z[24])
+                            + 2. * (z[3] + z[9] + z[15] + z[21] - z[1] - z[5] - z[19] - z[23]));
+
+                            q = 1. / (420. * res) * (44. * (z[3] + z[23] - z[1] - z[21]) + 31. * (z[0] + z[20] - z[4] - z[24]
+                            + 2. * (z[8] + z[18] - z[6] - z[16])) + 17. * (z[14] - z[10] + 4. * (z[13] - z[11]))
+                            + 5. * (z[9] + z[19] - z[5] - z[15]));
+
+                            p = 1. / (420. * res) * (44. * (z[5] + z[9] - z[15] - z[19]) + 31. * (z[20] + z[24] - z[0] - z[4]
+                                + 2. * (z[6] + z[8] - z[16] - z[18])) + 17. * (z[2] - z[22] + 4. * (z[7] - z[17]))
+                                + 5. * (z[1] + z[3] - z[21] - z[23]));
+
+                            value = curvature(&PartialDerivatives { p, q, r, s, t });
+                            if log_transform {
+                                // Based on Florinsky (2016) pg. 244 eq. 8.1
+                                value = value.signum() * (1. + log_multiplier * value.abs()).ln();
+                            }
+                            data[col as usize] = value;
+                        }
+                    }
+
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    } else { // geographic coordinates
+
+        let phi1 = input.get_y_from_row(0);
+        let lambda1 = input.get_x_from_column(0);
+
+        let phi2 = phi1;
+        let lambda2 = input.get_x_from_column(-1);
+
+        let linear_res = vincenty_distance((phi1, lambda1), (phi2, lambda2));
+        let lr2 =  haversine_distance((phi1, lambda1), (phi2, lambda2)); 
+        let diff = 100. * (linear_res - lr2).abs() / linear_res;
+        let use_haversine = diff < 0.5; // if the difference is less than 0.5%, use the faster haversine method to calculate distances.
+        
+        let log_multiplier = log_multiplier(linear_res);
+
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let curvature = curvature.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let mut z4: f64;
+                let mut p: f64;
+                let mut q: f64;
+                let mut r: f64;
+                let mut s: f64;
+                let mut t: f64;
+                let mut a: f64;
+                let mut b: f64;
+                let mut c: f64;
+                let mut d: f64;
+                let mut e: f64;
+                let mut phi1: f64;
+                let mut lambda1: f64;
+                let mut phi2: f64;
+                let mut lambda2: f64;
+                let mut value: f64;
+                let offsets = [
+                    [-1, -1], [0, -1], [1, -1], 
+                    [-1, 0], [0, 0], [1, 0], 
+                    [-1, 1], [0, 1], [1, 1]
+                ];
+                let mut z = [0f64; 25];
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z4 = input.get_value(row, col);
+                        if !is_nodata(z4, nodata) {
+                            for n in 0..9 {
+                                z[n] = input.get_value(row + offsets[n][1], col + offsets[n][0]);
+                                if !is_nodata(z[n], nodata) {
+                                    z[n] *= z_factor;
+                                } else {
+                                    z[n] = z4 * z_factor;
+                                }
+                            }
+
+                            // Calculate a, b, c, d, and e.
+                            phi1 = input.get_y_from_row(row);
+                            lambda1 = input.get_x_from_column(col);
+
+                            phi2 = phi1;
+                            lambda2 = input.get_x_from_column(col-1);
+
+                            b = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi2 = input.get_y_from_row(row+1);
+                            lambda2 = lambda1;
+
+                            d = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi2 = input.get_y_from_row(row-1);
+                            lambda2 = lambda1;
+
+                            e = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi1 = input.get_y_from_row(row+1);
+                            lambda1 = input.get_x_from_column(col);
+
+                            phi2 = phi1;
+                            lambda2 = input.get_x_from_column(col-1);
+
+                            a = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi1 = input.get_y_from_row(row-1);
+                            lambda1 = input.get_x_from_column(col);
+
+                            phi2 = phi1;
+                            lambda2 = input.get_x_from_column(col-1);
+
+                            c = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            /* 
+                            The following equations have been taken from Florinsky (2016) Principles and Methods
+                            of Digital Terrain Modelling, Chapter 4, pg. 117.
+                            */
+
+                            r = (c * c * (z[0] + z[2] - 2. * z[1]) + b * b * (z[3] + z[5] - 2. * z[4]) + a * a * (z[6] + z[8] - 2. * z[7]))
+                            / (a.powi(4) + b.powi(4) + c.powi(4));
+
+                            t = 2. / (3. * d * e * (d + e) * (a.powi(4) + b.powi(4) + c.powi(4)))
+                            * ((d * (a.powi(4) + b.powi(4) + b * b * c * c) - c * c * e * (a * a - b * b)) * (z[0] + z[2])
+                            - (d * (a.powi(4) + c.powi(4) + b * b * c * c) + e * (a.powi(4) + c.powi(4) + a * a * b * b)) * (z[3] + z[5])
+                            + (e * (b.powi(4) + c.powi(4) + a * a * b * b) + a * a * d * (b * b - c * c)) * (z[6] + z[8])
+                            + d * (b.powi(4) * (z[1] - 3. * z[4]) + c.powi(4) * (3. * z[1] - z[4]) + (a.powi(4) - 2. * b * b * c * c) * (z[1] - z[4]))
+                            + e * (a.powi(4) * (3. * z[7] - z[4]) + b.powi(4) * (z[7] - 3. * z[4]) + (c.powi(4) - 2. * a * a * b * b) * (z[7] - z[4]))
+                            - 2. * (a * a * d * (b * b - c * c) * z[7] - c * c * e * (a * a - b * b) * z[1]));
+
+                            s = (c * (a * a * (d + e) + b * b * e) * (z[2] - z[0]) - b * (a * a * d - c * c * e) * (z[3] - z[5]) + a * (c * c * (d + e) + b * b * d) * (z[6] - z[8]))
+                            / (2. * (a * a * c * c * (d + e).powi(2) + b * b * (a * a * d * d + c * c * e * e)));
+
+                            p = (a * a * c * d * (d + e) * (z[2] - z[0]) + b * (a * a * d * d + c * c * e * e) * (z[5] - z[3]) + a * c * c * e * (d + e) * (z[8] - z[6]))
+                            / (2. * (a * a * c * c * (d + e).powi(2) + b * b * (a * a * d * d + c * c * e * e)));
+
+                            q = 1. / (3. * d * e * (d + e) * (a.powi(4) + b.powi(4) + c.powi(4))) 
+                            * ((d * d * (a.powi(4) + b.powi(4) + b * b * c * c) + c * c * e * e * (a * a - b * b)) * (z[0] + z[2])
+                            - (d * d * (a.powi(4) + c.powi(4) + b * b * c * c) - e * e * (a.powi(4) + c.powi(4) + a * a * b * b)) * (z[3] + z[5])
+                            - (e * e * (b.powi(4) + c.powi(4) + a * a * b * b) - a * a * d * d * (b * b - c * c)) * (z[6] + z[8])
+                            + d * d * (b.powi(4) * (z[1] - 3. * z[4]) + c.powi(4) * (3. * z[1] - z[4]) + (a.powi(4) - 2. * b * b * c * c) * (z[1] - z[4]))
+                            + e * e * (a.powi(4) * (z[4] - 3. * z[7]) + b.powi(4) * (3. * z[4] - z[7]) + (c.powi(4) - 2. * a * a * b * b) * (z[4] - z[7]))
+                            - 2. * (a * a * d * d * (b * b - c * c) * z[7] + c * c * e * e * (a * a - b * b) * z[1]));
+
+                            value = curvature(&PartialDerivatives { p, q, r, s, t });
+                            if log_transform {
+                                // Based on Florinsky (2016) pg. 244 eq. 8.1
+                                value = value.signum() * (1. + log_multiplier * value.abs()).ln();
+                            }
+                            data[col as usize] = value;
+                        }
+                    }
+
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    }
+
+    let mut progress: usize;
+    let mut old_progress: usize = 1;
+    for row in 0..rows {
+        let (r, data) = rx.recv().expect("Error receiving data from thread.");
+        output.set_row_data(r, data);
+        reporter.check_cancelled()?;
+        if verbose {
+            progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Progress", progress);
+                old_progress = progress;
+            }
+        }
+    }
+
+    Ok(())
+}
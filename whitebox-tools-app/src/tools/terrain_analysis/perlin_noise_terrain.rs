@@ -0,0 +1,244 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::synthetic_terrain::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool creates a synthetic terrain (`--output`) of fractal Perlin noise, i.e. the sum of a
+/// number of octaves (`--octaves`) of Perlin gradient noise. The first octave has features of the
+/// given wavelength (`--wavelength`, in map units), which defaults to one-quarter of the smaller
+/// dimension of the grid, and each successive octave has half the wavelength of the last and its
+/// amplitude multiplied by the persistence (`--persistence`); higher persistence gives rougher
+/// surfaces. The elevations are scaled to range from zero to the relief (`--relief`). Unlike the
+/// surfaces of `FractalTerrain`, Perlin noise surfaces are statistically the same everywhere, and
+/// they can be generated for grids of any size without a large intermediate grid.
+///
+/// The output grid is that of a base raster (`--base`), if one is given, and otherwise has the
+/// given numbers of rows (`--rows`) and columns (`--columns`) and cell size (`--cell_size`), with
+/// its lower-left corner at the origin. The surface is determined by its parameters and the random
+/// seed (`--seed`); the same seed gives the same surface on every platform.
+///
+/// # See Also
+/// `FractalTerrain`, `GaussianHills`, `SyntheticValley`, `TurningBandsSimulation`
+pub struct PerlinNoiseTerrain {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl PerlinNoiseTerrain {
+    pub fn new() -> PerlinNoiseTerrain {
+        // public constructor
+        let name = "PerlinNoiseTerrain".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Creates a seeded synthetic terrain of fractal Perlin noise.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.extend(grid_parameters());
+
+        parameters.push(ToolParameter {
+            name: "Wavelength".to_owned(),
+            flags: vec!["--wavelength".to_owned()],
+            description: "Wavelength of the first octave, in map units. Defaults to one-quarter of the smaller grid dimension.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Octaves".to_owned(),
+            flags: vec!["--octaves".to_owned()],
+            description: "Number of octaves of noise.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("6".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Persistence".to_owned(),
+            flags: vec!["--persistence".to_owned()],
+            description: "Amplitude of each octave relative to the last (0-1).".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Relief".to_owned(),
+            flags: vec!["--relief".to_owned()],
+            description: "Range of the output elevations.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("100.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(seed_parameter());
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -o=perlin.tif --base=dem.tif --wavelength=2000.0 --octaves=8 --persistence=0.45 --relief=300.0 --seed=11", short_exe, name).replace("*", &sep);
+
+        PerlinNoiseTerrain {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for PerlinNoiseTerrain {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let output_file = resolve_path(&parsed.get_string("output").unwrap_or_default(), working_directory);
+        let wavelength = parsed.get_f64("wavelength");
+        let octaves = parsed.get_usize("octaves")?.unwrap_or(6);
+        let persistence = parsed.get_f64("persistence").unwrap_or(0.5);
+        let relief = parsed.get_f64("relief").unwrap_or(100.0);
+        let seed = parsed.get_i64("seed").unwrap_or(42) as u64;
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if octaves == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --octaves parameter must be at least 1.",
+            ));
+        }
+        if !(persistence > 0.0 && persistence <= 1.0) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --persistence parameter must be greater than 0 and no greater than 1.",
+            ));
+        }
+
+        let configs = output_configs(&parsed, working_directory)?;
+        let wavelength = wavelength.unwrap_or(0.25 * (configs.north - configs.south).min(configs.east - configs.west));
+        if wavelength <= 0.0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --wavelength parameter must be greater than zero.",
+            ));
+        }
+
+        let start = Instant::now();
+
+        // Noise is evaluated relative to the lower-left corner of the grid, so that the surface
+        // doesn't depend on where the grid is located.
+        let rows = configs.rows as f64;
+        let (res_x, res_y) = (configs.resolution_x, configs.resolution_y);
+        let mut surface = compute_surface(
+            configs.rows as isize,
+            configs.columns as isize,
+            move |row, col| {
+                let x = (col as f64 + 0.5) * res_x;
+                let y = (rows - row as f64 - 0.5) * res_y;
+                fractal_perlin(x, y, wavelength, octaves, persistence, seed)
+            },
+            verbose,
+            reporter,
+        )?;
+        rescale(&mut surface, relief);
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let metadata = vec![
+            format!("Created by whitebox_tools\' {} tool", self.get_tool_name()),
+            format!("Wavelength: {}", wavelength),
+            format!("Octaves: {}", octaves),
+            format!("Persistence: {}", persistence),
+            format!("Relief: {}", relief),
+            format!("Seed: {}", seed),
+            format!("Elapsed Time (excluding I/O): {}", elapsed_time),
+        ];
+        write_surface(&output_file, &configs, surface, metadata, verbose)?;
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
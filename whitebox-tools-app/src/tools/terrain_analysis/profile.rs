@@ -2,20 +2,16 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 21/02/2018
-Last Modified: 12/10/2018
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_raster::*;
-use whitebox_common::rendering::html::*;
-use whitebox_common::rendering::LineGraph;
+use whitebox_common::rendering::{LineGraph, Report};
 use crate::tools::*;
 use whitebox_vector::{ShapeType, Shapefile};
 use std::env;
 use std::f64;
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::BufWriter;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::process::Command;
@@ -24,7 +20,9 @@ use std::process::Command;
 /// an input (`--surface`) digital elevation model (DEM), or other surface model. The data profile plots
 /// surface height (y-axis) against distance along profile (x-axis). The tool outputs an interactive SVG line
 /// graph embedded in an HTML document (`--output`). If the vector lines file contains multiple line features,
-/// the output plot will contain each of the input profiles.
+/// the output plot will contain each of the input profiles. When the `report_json` setting is enabled (e.g.
+/// `--report_json`), the distance and elevation values of each profile are also written to a JSON file of
+/// the same name.
 ///
 /// If you want to extract the [longitudinal profile](http://www.fao.org/docrep/003/X6841E/X6841E02.HTM) of a river,
 /// use the `LongProfile` tool instead.
@@ -141,47 +139,12 @@ impl WhiteboxTool for Profile {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut profile_file = String::new();
-        let mut surface_file = String::new();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-lines" {
-                profile_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-surface" {
-                surface_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut profile_file = parsed.get_string("lines").unwrap_or_default();
+        let mut surface_file = parsed.get_string("surface").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -194,20 +157,12 @@ impl WhiteboxTool for Profile {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !profile_file.contains(&sep) && !profile_file.contains("/") {
-            profile_file = format!("{}{}", working_directory, profile_file);
-        }
-        if !surface_file.contains(&sep) && !surface_file.contains("/") {
-            surface_file = format!("{}{}", working_directory, surface_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        profile_file = resolve_input_path(&profile_file, working_directory)?;
+        surface_file = resolve_input_path(&surface_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading profile data...")
@@ -296,7 +251,7 @@ impl WhiteboxTool for Profile {
                             row = (st_row as f64 + j as f64 * dy) as isize;
                             z = surface.get_value(row, col);
                             dist += dist_step;
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 profile_xdata.push(dist);
                                 profile_ydata.push(z);
                             }
@@ -315,48 +270,24 @@ impl WhiteboxTool for Profile {
                 }
             }
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * record_num as f64 / (profile_data.num_records - 1) as f64)
                     as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
         }
 
-        let f = File::create(output_file.clone())?;
-        let mut writer = BufWriter::new(f);
-
-        writer.write_all(&r#"<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">
-        <head>
-            <meta content=\"text/html; charset=UTF-8\" http-equiv=\"content-type\">
-            <title>Profile</title>"#.as_bytes())?;
-
-        // get the style sheet
-        writer.write_all(&get_css().as_bytes())?;
-
-        writer.write_all(
-            &r#"</head>
-        <body>
-            <h1>Profile</h1>"#
-                .as_bytes(),
-        )?;
-
-        writer.write_all(
-            (format!(
-                "<p><strong>Input Surface</strong>: {}<br>",
-                surface.get_short_filename()
-            ))
-            .as_bytes(),
-        )?;
-
-        writer.write_all(("</p>").as_bytes())?;
         let elapsed_time = get_formatted_elapsed_time(start);
 
         let multiples = xdata.len() > 2 && xdata.len() < 12;
 
-        let graph = LineGraph {
+        let mut report = Report::new("Profile");
+        report.add_property("Input Surface", surface.get_short_filename());
+        report.add_line_graph(LineGraph {
             parent_id: "graph".to_string(),
             width: 700f64,
             height: 500f64,
@@ -369,15 +300,8 @@ impl WhiteboxTool for Profile {
             draw_gridlines: true,
             draw_legend: multiples,
             draw_grey_background: false,
-        };
-
-        writer.write_all(
-            &format!("<div id='graph' align=\"center\">{}</div>", graph.get_svg()).as_bytes(),
-        )?;
-
-        writer.write_all("</body>".as_bytes())?;
-
-        let _ = writer.flush();
+        });
+        report.write(&output_file)?;
 
         if verbose {
             println!(
@@ -0,0 +1,51 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+// Model parameters that may be specified either as a constant or as a raster, shared by the
+// ShallowLandslideSusceptibility and TerrainAdjustedDegreeDays tools.
+
+use whitebox_raster::{is_nodata, Raster};
+use whitebox_common::utils::resolve_input_path;
+use std::io::Error;
+
+/// A model parameter that is either spatially constant or read from a raster.
+pub(super) enum RasterOrConstant {
+    Constant(f64),
+    Raster(Raster),
+}
+
+impl RasterOrConstant {
+    /// Parses a parameter value, which is either a number or the name of a raster that must
+    /// share the dimensions of the `base` raster.
+    pub(super) fn new(value: &str, working_directory: &str, base: &Raster) -> Result<RasterOrConstant, Error> {
+        if let Ok(v) = value.parse::<f64>() {
+            return Ok(RasterOrConstant::Constant(v));
+        }
+        let file = resolve_input_path(value, working_directory)?;
+        let raster = Raster::new(&file, "r")?;
+        if raster.configs.rows != base.configs.rows || raster.configs.columns != base.configs.columns {
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
+        }
+        Ok(RasterOrConstant::Raster(raster))
+    }
+
+    /// Returns the parameter value at a grid cell, or `None` for raster nodata cells.
+    pub(super) fn get_value(&self, row: isize, col: isize) -> Option<f64> {
+        match self {
+            RasterOrConstant::Constant(v) => Some(*v),
+            RasterOrConstant::Raster(r) => {
+                let v = r.get_value(row, col);
+                if !is_nodata(v, r.configs.nodata) {
+                    Some(v)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
@@ -8,14 +8,13 @@ License: MIT
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to calculate the relative stream power (*RSP*) index. This index is directly related
 /// to the stream power if the assumption can be made that discharge is directly proportional to upslope
@@ -155,60 +154,13 @@ impl WhiteboxTool for StreamPowerIndex {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut sca_file = String::new();
-        let mut slope_file = String::new();
-        let mut output_file = String::new();
-        let mut sca_exponent = 1.0;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-sca" {
-                if keyval {
-                    sca_file = vec[1].to_string();
-                } else {
-                    sca_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-slope" {
-                if keyval {
-                    slope_file = vec[1].to_string();
-                } else {
-                    slope_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-exponent" {
-                if keyval {
-                    sca_exponent = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    sca_exponent = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut sca_file = parsed.get_string("sca").unwrap_or_default();
+        let mut slope_file = parsed.get_string("slope").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let sca_exponent = parsed.get_f64("exponent").unwrap_or(1.0);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -221,20 +173,12 @@ impl WhiteboxTool for StreamPowerIndex {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
-        if !sca_file.contains(&sep) && !sca_file.contains("/") {
-            sca_file = format!("{}{}", working_directory, sca_file);
-        }
-        if !slope_file.contains(&sep) && !slope_file.contains("/") {
-            slope_file = format!("{}{}", working_directory, slope_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
+        sca_file = resolve_input_path(&sca_file, working_directory)?;
+        slope_file = resolve_input_path(&slope_file, working_directory)?;
 
         if verbose {
             println!("Reading data...")
@@ -250,25 +194,19 @@ impl WhiteboxTool for StreamPowerIndex {
 
         // make sure the input files have the same size
         if sca.configs.rows != slope.configs.rows || sca.configs.columns != slope.configs.columns {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "The input files must have the same number of rows and columns and spatial extent.",
-            ));
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
         }
+        warn_if_crs_differs(&sca, &slope, verbose);
 
         // calculate the number of downslope cells
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let sca = sca.clone();
             let slope = slope.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut sca_val: f64;
                 let mut slope_val: f64;
                 for row in (0..rows).filter(|r| r % num_procs == tid) {
@@ -276,12 +214,14 @@ impl WhiteboxTool for StreamPowerIndex {
                     for col in 0..columns {
                         sca_val = sca[(row, col)];
                         slope_val = slope[(row, col)];
-                        if sca_val != sca_nodata && slope_val != slope_nodata {
+                        if !is_nodata(sca_val, sca_nodata) && !is_nodata(slope_val, slope_nodata) {
                             data[col as usize] =
                                 sca_val.powf(sca_exponent) * slope_val.to_radians().tan();
                         }
                     }
-                    tx.send((row, data)).unwrap();
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -291,10 +231,11 @@ impl WhiteboxTool for StreamPowerIndex {
             let (row, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(row, data);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
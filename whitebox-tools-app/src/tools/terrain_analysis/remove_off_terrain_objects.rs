@@ -8,18 +8,17 @@ License: MIT
 Note: This algorithm could be parallelized
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::structures::{Array2D, DistanceMetric, FixedRadiusSearch2D};
 use crate::tools::*;
-use num_cpus;
 use std::collections::VecDeque;
 use std::env;
 use std::f32;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to create a bare-earth DEM from a fine-resolution digital surface model. The
 /// tool is typically applied to LiDAR DEMs which frequently contain numerous off-terrain objects (OTOs) such
@@ -158,68 +157,13 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut filter_size = 11usize;
-        let mut slope_threshold = 15f32;
-        let mut keyval: bool;
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-filter" {
-                if keyval {
-                    filter_size = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                } else {
-                    filter_size = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        as usize;
-                }
-            } else if flag_val == "-slope" {
-                if keyval {
-                    slope_threshold = vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    slope_threshold = args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut filter_size = parsed.get_usize("filter")?.unwrap_or(11usize);
+        let slope_threshold = parsed.get_f64("slope").map(|v| v as f32).unwrap_or(15f32);
         if verbose {
             let tool_name = self.get_tool_name();
             let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28); 
@@ -231,8 +175,6 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         // The filter dimensions must be odd numbers such that there is a middle pixel
         if (filter_size as f32 / 2f32).floor() == (filter_size as f32 / 2f32) {
             filter_size += 1;
@@ -245,12 +187,8 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -285,17 +223,13 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
             println!("Performing tophat transform...")
         };
 
-        let mut num_procs = num_cpus::get() as isize;
         let configurations = whitebox_common::configs::get_configs()?;
-        let max_procs = configurations.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configurations).num_threads() as isize;
         let (tx, rx) = mpsc::channel();
         for tid in 0..num_procs {
             let input = input.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f32;
                 let mut z_n: f32;
                 let mut min_val: f32;
@@ -312,7 +246,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             min_val = f32::INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = input.get_value(row2, col + midpoint);
-                                if z_n < min_val && z_n != nodata {
+                                if z_n < min_val && !is_nodata(z_n as f64, nodata as f64) {
                                     min_val = z_n;
                                 }
                             }
@@ -325,7 +259,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                                 min_val = f32::INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = input.get_value(row2, col2);
-                                    if z_n < min_val && z_n != nodata {
+                                    if z_n < min_val && !is_nodata(z_n as f64, nodata as f64) {
                                         min_val = z_n;
                                     }
                                 }
@@ -333,7 +267,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z as f64, nodata as f64) {
                             min_val = f32::INFINITY;
                             for v in filter_vals.iter() {
                                 if *v < min_val {
@@ -343,7 +277,9 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             erosion[col as usize] = min_val;
                         }
                     }
-                    tx.send((row, erosion)).unwrap();
+                    if tx.send((row, erosion)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -354,10 +290,11 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
             let data = rx.recv().expect("Error receiving data from thread.");
             erosion.set_row_data(data.0, data.1);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f32 * r as f32 / (rows - 1) as f32) as usize;
                 if progress != old_progress {
-                    println!("Performing erosion: {}%", progress);
+                    reporter.progress("Performing erosion", progress);
                     old_progress = progress;
                 }
             }
@@ -369,7 +306,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
             let input = input.clone();
             let erosion = erosion.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            spawn(move || {
                 let mut z: f32;
                 let mut z_n: f32;
                 let mut max_val: f32;
@@ -387,7 +324,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             max_val = f32::NEG_INFINITY;
                             for row2 in start_row..end_row + 1 {
                                 z_n = erosion.get_value(row2, col + midpoint);
-                                if z_n > max_val && z_n != nodata {
+                                if z_n > max_val && !is_nodata(z_n as f64, nodata as f64) {
                                     max_val = z_n;
                                 }
                             }
@@ -400,7 +337,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                                 max_val = f32::NEG_INFINITY;
                                 for row2 in start_row..end_row + 1 {
                                     z_n = erosion.get_value(row2, col2);
-                                    if z_n > max_val && z_n != nodata {
+                                    if z_n > max_val && !is_nodata(z_n as f64, nodata as f64) {
                                         max_val = z_n;
                                     }
                                 }
@@ -408,7 +345,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             }
                         }
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z as f64, nodata as f64) {
                             max_val = f32::NEG_INFINITY;
                             for v in filter_vals.iter() {
                                 if *v > max_val {
@@ -421,7 +358,9 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                             }
                         }
                     }
-                    tx.send((row, tophat, opening)).unwrap();
+                    if tx.send((row, tophat, opening)).is_err() {
+                        return;
+                    }
                 }
             });
         }
@@ -435,10 +374,11 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
             tophat.set_row_data(data.0, data.1);
             opening.set_row_data(data.0, data.2);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f32 * r as f32 / (rows - 1) as f32) as usize;
                 if progress != old_progress {
-                    println!("Performing dilation: {}%", progress);
+                    reporter.progress("Performing dilation", progress);
                     old_progress = progress;
                 }
             }
@@ -463,7 +403,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
         for row in 0..rows {
             for col in 0..columns {
                 out.set_value(row, col, initial_value);
-                if tophat.get_value(row, col) != nodata {
+                if !is_nodata(tophat.get_value(row, col) as f64, nodata as f64) {
                     if tophat.get_value(row, col) <= height_diff_threshold[1] {
                         stack.push(GridCell {
                             row: row,
@@ -475,16 +415,18 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                     out.set_value(row, col, nodata);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f32 * row as f32 / (rows - 1) as f32) as usize;
                 if progress != old_progress {
-                    println!("Finding seed cells: {}%", progress);
+                    reporter.progress("Finding seed cells", progress);
                     old_progress = progress;
                 }
             }
         }
 
         while stack.len() > 0 {
+            reporter.check_cancelled()?;
             let gc = stack.pop().expect("Error during pop operation.");
             row = gc.row;
             col = gc.column;
@@ -493,7 +435,7 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                 row_n = row + d_y[i];
                 col_n = col + d_x[i];
                 z_n = tophat.get_value(row_n, col_n);
-                if z_n != nodata && out[(row_n, col_n)] == initial_value {
+                if !is_nodata(z_n as f64, nodata as f64) && out[(row_n, col_n)] == initial_value {
                     if z_n - z < height_diff_threshold[i] {
                         out[(row_n, col_n)] = z_n;
                         stack.push(GridCell {
@@ -517,12 +459,12 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
         ); // 4X
         for row in 0..rows {
             for col in 0..columns {
-                if tophat.get_value(row, col) != nodata && out.get_value(row, col) != initial_value
+                if !is_nodata(tophat.get_value(row, col) as f64, nodata as f64) && out.get_value(row, col) != initial_value
                 {
                     for i in 0..8 {
                         row_n = row + d_y[i];
                         col_n = col + d_x[i];
-                        if tophat.get_value(row_n, col_n) != nodata
+                        if !is_nodata(tophat.get_value(row_n, col_n) as f64, nodata as f64)
                             && out.get_value(row_n, col_n) == initial_value
                         {
                             frs.insert(
@@ -535,10 +477,11 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                     }
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f32 * row as f32 / (rows - 1) as f32) as usize;
                 if progress != old_progress {
-                    println!("Finding OTO edge cells: {}%", progress);
+                    reporter.progress("Finding OTO edge cells", progress);
                     old_progress = progress;
                 }
             }
@@ -577,10 +520,11 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
                     );
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f32 * row as f32 / (rows - 1) as f32) as usize;
                 if progress != old_progress {
-                    println!("Interpolating data holes: {}%", progress);
+                    reporter.progress("Interpolating data holes", progress);
                     old_progress = progress;
                 }
             }
@@ -594,17 +538,18 @@ impl WhiteboxTool for RemoveOffTerrainObjects {
         let mut output = Raster::initialize_using_config(&output_file, &configs); // 8X
         for row in 0..rows {
             for col in 0..columns {
-                if out.get_value(row, col) != initial_value && tophat.get_value(row, col) != nodata
+                if out.get_value(row, col) != initial_value && !is_nodata(tophat.get_value(row, col) as f64, nodata as f64)
                 {
                     output.set_value(row, col, out[(row, col)] as f64);
                 } else {
                     output.set_value(row, col, nodata as f64);
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f32 * row as f32 / (rows - 1) as f32) as usize;
                 if progress != old_progress {
-                    println!("Outputting data: {}%", progress);
+                    reporter.progress("Outputting data", progress);
                     old_progress = progress;
                 }
             }
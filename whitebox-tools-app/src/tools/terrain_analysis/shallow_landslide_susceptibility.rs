@@ -0,0 +1,431 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::raster_or_constant::RasterOrConstant;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool models the susceptibility of the landscape to shallow landsliding using the infinite slope
+/// stability model coupled with a steady-state hydrological model, in the manner of SHALSTAB (Montgomery and
+/// Dietrich, 1994) and SINMAP (Pack et al., 1998). The required inputs are a specific contributing area (SCA)
+/// raster (`--sca`), i.e. the upslope contributing area per unit contour length, and a slope raster (`--slope`),
+/// measured in degrees. The SCA should be calculated using one of the flow accumulation tools, e.g.
+/// `DInfFlowAccumulation` or `FD8FlowAccumulation`, and must not be log-transformed.
+///
+/// The relative wetness of the soil, *w*, is the ratio of the depth of saturated subsurface flow to the soil
+/// depth under steady-state recharge:
+///
+/// > w = min(R a / (T sin θ), 1)
+///
+/// where *a* is the SCA, θ is the slope angle, *R* is the steady-state recharge (`--recharge`), and *T* is the
+/// soil transmissivity (`--transmissivity`). The factor of safety (FS) of an infinite slope is then:
+///
+/// > FS = (C + cos θ (1 - w r) tan φ) / sin θ
+///
+/// where *C* is the dimensionless combined root and soil cohesion (`--cohesion`), i.e. the cohesion divided by
+/// the product of the soil depth, soil density, and gravitational acceleration, φ is the internal friction angle
+/// of the soil (`--friction_angle`, in degrees), and *r* is the ratio of the density of water to that of the soil
+/// (`--density_ratio`). Cells with an FS less than one are predicted to be unstable under the specified recharge.
+///
+/// The critical steady-state rainfall (recharge) required to raise the relative wetness to the point of failure
+/// (FS = 1) is found by solving the equation above for *R*:
+///
+/// > R<sub>c</sub> = (T sin θ / a) (1 - (sin θ - C) / (cos θ tan φ)) / r
+///
+/// Critical rainfall is measured in the same units as the recharge. Low values indicate sites that require
+/// little rainfall to fail and are therefore highly susceptible. Cells that are unstable even when dry
+/// (unconditionally unstable) are assigned a critical rainfall of zero, while cells that remain stable when fully
+/// saturated (unconditionally stable) are assigned a value of -1.
+///
+/// Each of the soil parameters may be specified either as a constant value or as a raster, allowing for spatially
+/// variable soil properties. Input rasters must have the same dimensions as the SCA raster. The transmissivity
+/// and recharge must be expressed using the same length and time units, with the SCA expressed in the same length
+/// unit (e.g. T in m<sup>2</sup>/day, R in m/day, and SCA in m). At least one of the factor of safety
+/// (`--out_fs`) and critical rainfall (`--out_rainfall`) outputs must be specified. Grid cells that are flat, or
+/// that possess the NoData value in any of the inputs, are assigned NoData in the outputs.
+///
+/// # References
+/// Montgomery, D. R., and Dietrich, W. E. (1994). A physically based model for the topographic control on
+/// shallow landsliding. *Water Resources Research*, 30(4), 1153-1171.
+///
+/// Pack, R. T., Tarboton, D. G., and Goodwin, C. N. (1998). The SINMAP approach to terrain stability mapping.
+/// *8th Congress of the International Association of Engineering Geology*, Vancouver, British Columbia.
+///
+/// # See Also
+/// `WetnessIndex`, `Slope`, `DInfFlowAccumulation`, `FD8FlowAccumulation`
+pub struct ShallowLandslideSusceptibility {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl ShallowLandslideSusceptibility {
+    pub fn new() -> ShallowLandslideSusceptibility {
+        // public constructor
+        let name = "ShallowLandslideSusceptibility".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Models shallow landslide susceptibility using the infinite slope stability model and steady-state wetness.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input Specific Contributing Area (SCA) File".to_owned(),
+            flags: vec!["--sca".to_owned()],
+            description: "Input raster specific contributing area (SCA) file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Slope File".to_owned(),
+            flags: vec!["--slope".to_owned()],
+            description: "Input raster slope file (in degrees).".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Dimensionless Cohesion".to_owned(),
+            flags: vec!["--cohesion".to_owned()],
+            description: "Dimensionless combined root and soil cohesion; a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: Some("0.1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Internal Friction Angle (degrees)".to_owned(),
+            flags: vec!["--friction_angle".to_owned()],
+            description: "Soil internal friction angle, in degrees; a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: Some("35.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Water-to-Soil Density Ratio".to_owned(),
+            flags: vec!["--density_ratio".to_owned()],
+            description: "Ratio of the density of water to the density of soil; a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Transmissivity".to_owned(),
+            flags: vec!["--transmissivity".to_owned()],
+            description: "Soil transmissivity (e.g. m^2/day); a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: Some("50.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Steady-State Recharge".to_owned(),
+            flags: vec!["--recharge".to_owned()],
+            description: "Steady-state recharge (e.g. m/day); a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: Some("0.025".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Factor of Safety File (optional)".to_owned(),
+            flags: vec!["--out_fs".to_owned()],
+            description: "Output factor of safety raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Critical Rainfall File (optional)".to_owned(),
+            flags: vec!["--out_rainfall".to_owned()],
+            description: "Output critical steady-state rainfall raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --sca=sca.tif --slope=slope.tif --cohesion=0.1 --friction_angle=friction.tif --density_ratio=0.5 --transmissivity=50.0 --recharge=0.025 --out_fs=fs.tif --out_rainfall=critical_rainfall.tif",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        ShallowLandslideSusceptibility {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for ShallowLandslideSusceptibility {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut sca_file = parsed.get_string("sca").unwrap_or_default();
+        let mut slope_file = parsed.get_string("slope").unwrap_or_default();
+        let cohesion = parsed.get_string("cohesion").unwrap_or("0.1".to_string());
+        let friction_angle = parsed.get_string("friction_angle").unwrap_or("35.0".to_string());
+        let density_ratio = parsed.get_string("density_ratio").unwrap_or("0.5".to_string());
+        let transmissivity = parsed.get_string("transmissivity").unwrap_or("50.0".to_string());
+        let recharge = parsed.get_string("recharge").unwrap_or("0.025".to_string());
+        let mut fs_file = parsed.get_string("out_fs").unwrap_or_default();
+        let mut rainfall_file = parsed.get_string("out_rainfall").unwrap_or_default();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if fs_file.is_empty() && rainfall_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one of the factor of safety (--out_fs) and critical rainfall (--out_rainfall) outputs must be specified.",
+            ));
+        }
+        for file in [&mut sca_file, &mut slope_file, &mut fs_file, &mut rainfall_file] {
+            *file = resolve_path(file, working_directory);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let sca = Arc::new(Raster::new(&sca_file, "r")?);
+        let slope = Arc::new(Raster::new(&slope_file, "r")?);
+        let rows = sca.configs.rows as isize;
+        let columns = sca.configs.columns as isize;
+        let sca_nodata = sca.configs.nodata;
+        let slope_nodata = slope.configs.nodata;
+        if slope.configs.rows != sca.configs.rows || slope.configs.columns != sca.configs.columns {
+            return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
+        }
+        warn_if_crs_differs(&slope, &sca, verbose);
+
+        let mut soil_params = Vec::with_capacity(5);
+        for value in [&cohesion, &friction_angle, &density_ratio, &transmissivity, &recharge] {
+            soil_params.push(RasterOrConstant::new(value, working_directory, &sca)?);
+        }
+        let soil_params = Arc::new(soil_params);
+
+        let start = Instant::now();
+
+        let out_nodata = -32768f64;
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let sca = sca.clone();
+            let slope = slope.clone();
+            let soil_params = soil_params.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let mut params = [0f64; 5];
+                let (mut a, mut theta, mut sin_theta, mut cos_theta, mut tan_phi): (f64, f64, f64, f64, f64);
+                let (mut c, mut r, mut t, mut q, mut w, mut wc): (f64, f64, f64, f64, f64, f64);
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut fs_data = vec![out_nodata; columns as usize];
+                    let mut rainfall_data = vec![out_nodata; columns as usize];
+                    'col: for col in 0..columns {
+                        a = sca.get_value(row, col);
+                        theta = slope.get_value(row, col);
+                        if is_nodata(a, sca_nodata) || is_nodata(theta, slope_nodata) || theta <= 0f64 || a <= 0f64 {
+                            continue;
+                        }
+                        for i in 0..5 {
+                            params[i] = match soil_params[i].get_value(row, col) {
+                                Some(v) => v,
+                                None => continue 'col,
+                            };
+                        }
+                        c = params[0];
+                        tan_phi = params[1].to_radians().tan();
+                        r = params[2];
+                        t = params[3];
+                        q = params[4];
+                        if t <= 0f64 || r <= 0f64 {
+                            continue;
+                        }
+                        theta = theta.to_radians();
+                        sin_theta = theta.sin();
+                        cos_theta = theta.cos();
+
+                        w = (q * a / (t * sin_theta)).min(1f64);
+                        fs_data[col as usize] = (c + cos_theta * (1f64 - w * r) * tan_phi) / sin_theta;
+
+                        wc = if tan_phi > 0f64 {
+                            (1f64 - (sin_theta - c) / (cos_theta * tan_phi)) / r
+                        } else if c >= sin_theta {
+                            f64::INFINITY
+                        } else {
+                            f64::NEG_INFINITY
+                        };
+                        rainfall_data[col as usize] = if wc <= 0f64 {
+                            0f64 // unconditionally unstable
+                        } else if wc >= 1f64 {
+                            -1f64 // unconditionally stable
+                        } else {
+                            wc * t * sin_theta / a
+                        };
+                    }
+                    if tx.send((row, fs_data, rainfall_data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let initialize_output = |file: &str| {
+            if file.is_empty() {
+                return None;
+            }
+            let mut output = Raster::initialize_using_file(file, &sca);
+            output.configs.nodata = out_nodata;
+            output.configs.data_type = DataType::F32;
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.palette = "spectrum.plt".to_string();
+            Some(output)
+        };
+        let mut fs_output = initialize_output(&fs_file);
+        let mut rainfall_output = initialize_output(&rainfall_file);
+        for r in 0..rows {
+            let (row, fs_data, rainfall_data) = rx.recv().expect("Error receiving data from thread.");
+            if let Some(ref mut output) = fs_output {
+                output.set_row_data(row, fs_data);
+            }
+            if let Some(ref mut output) = rainfall_output {
+                output.set_row_data(row, rainfall_data);
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        for (output, label) in [
+            (fs_output, "Factor of safety"),
+            (rainfall_output, "Critical rainfall"),
+        ] {
+            let mut output = match output {
+                Some(output) => output,
+                None => continue,
+            };
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("{}", label));
+            output.add_metadata_entry(format!("SCA raster: {}", sca_file));
+            output.add_metadata_entry(format!("Slope raster: {}", slope_file));
+            output.add_metadata_entry(format!("Cohesion: {}", cohesion));
+            output.add_metadata_entry(format!("Friction angle: {}", friction_angle));
+            output.add_metadata_entry(format!("Density ratio: {}", density_ratio));
+            output.add_metadata_entry(format!("Transmissivity: {}", transmissivity));
+            output.add_metadata_entry(format!("Recharge: {}", recharge));
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if sca.configs.maximum < 100.0 {
+            println!("WARNING: The input SCA data layer contained only low values. It is likely that it has been
+            log-transformed. This tool requires non-transformed SCA as an input.")
+        }
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::horizon_search::{ground_cell_sizes, horizon_search};
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::Arc;
+
+/// This tool calculates the sky-view factor (SVF) of each grid cell in an input digital elevation model
+/// (`--dem`). The sky-view factor is the proportion of the visible sky hemisphere above a location that is not
+/// obscured by the surrounding terrain (Zakšek et al., 2011). For each of a number of equally spaced azimuths
+/// (`--num_directions`), a ray is traced outward from the cell to a maximum search distance (`--max_dist`) and
+/// the horizon elevation angle, γ, is found. The SVF is then estimated as:
+///
+/// > SVF = 1 - Σ sin(γ<sub>i</sub>) / n
+///
+/// where *n* is the number of directions and negative horizon angles are treated as zero. SVF values range from
+/// 0 to 1; a value of 1 indicates that the whole sky hemisphere is visible, as is the case on flat terrain and
+/// on isolated peaks, while low values occur in deep valleys, pits, and trenches.
+///
+/// Like openness, the sky-view factor does not depend on an illumination direction and is commonly used to
+/// visualize subtle relief in high-resolution DEMs, particularly in archaeological prospection. It is also an
+/// input to models of diffuse solar radiation. The search distance is measured in the horizontal units of the
+/// DEM (or metres for DEMs in geographic coordinates) and defaults to ten grid cells. The horizon search is
+/// shared with the `Openness` tool.
+///
+/// # Reference
+/// Zakšek, K., Oštir, K., and Kokalj, Ž. (2011). Sky-view factor as a relief visualization technique.
+/// *Remote Sensing*, 3(2), 398-415.
+///
+/// # See Also
+/// `Openness`, `LocalDominance`, `HorizonAngle`, `Hillshade`
+pub struct SkyViewFactor {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SkyViewFactor {
+    pub fn new() -> SkyViewFactor {
+        // public constructor
+        let name = "SkyViewFactor".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Calculates the sky-view factor of a DEM.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Maximum Search Distance (optional)".to_owned(),
+            flags: vec!["--max_dist".to_owned()],
+            description: "Maximum search distance, in map units; defaults to ten grid cells.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Number of Search Directions".to_owned(),
+            flags: vec!["--num_directions".to_owned()],
+            description: "Number of equally spaced search directions.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("16".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif -o=svf.tif --max_dist=25.0 --num_directions=16",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SkyViewFactor {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SkyViewFactor {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut max_dist = parsed.get_f64("max_dist").unwrap_or(0f64);
+        let mut num_directions = parsed.get_usize("num_directions")?.unwrap_or(16usize);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        if num_directions < 4 {
+            num_directions = 4;
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+
+        let start = Instant::now();
+
+        let (cell_size_x, cell_size_y) = ground_cell_sizes(&input);
+        if max_dist <= 0f64 {
+            max_dist = 10f64 * cell_size_x.max(cell_size_y);
+        }
+        if max_dist < cell_size_x.max(cell_size_y) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The maximum search distance (--max_dist) must be at least one grid cell.",
+            ));
+        }
+
+        let outputs = horizon_search(
+            &input,
+            num_directions,
+            max_dist,
+            1,
+            move |max_angles, _min_angles, values| {
+                let n = max_angles.len() as f64;
+                let obscured = max_angles.iter().map(|a| a.max(0f64).sin()).sum::<f64>();
+                values[0] = 1f64 - obscured / n;
+            },
+            verbose,
+            reporter,
+        )?;
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.data_type = DataType::F32;
+        output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+        output.configs.palette = "grey.plt".to_string();
+        output.configs.z_units = "dimensionless".to_string();
+        for row in 0..outputs[0].rows() {
+            output.set_row_data(row, outputs[0].get_row_data(row));
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Maximum search distance: {}", max_dist));
+        output.add_metadata_entry(format!("Number of directions: {}", num_directions));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
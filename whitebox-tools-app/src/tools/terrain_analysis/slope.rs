@@ -2,20 +2,20 @@
 This tool is part of the WhiteboxTools geospatial analysis library.
 Authors: Dr. John Lindsay
 Created: 22/06/2017
-Last Modified: 15/01/2022
+Last Modified: 16/10/2026
 License: MIT
 */
 
 use whitebox_raster::*;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
+use std::ops::Range;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 use whitebox_common::utils::{
     get_formatted_elapsed_time, 
     haversine_distance,
@@ -35,6 +35,15 @@ use whitebox_common::utils::{
 /// (i.e. angular units), the tool uses the 3x3 polynomial fitting method for equal angle grids also 
 /// described by Florinsky (2016). 
 ///
+/// When the tool is run with the `--compute=gpu` flag and WhiteboxTools has been built with the
+/// `gpu` feature, slope is calculated for projected DEMs on the GPU, in single precision. If no
+/// suitable GPU is found, the tool falls back to the CPU.
+///
+/// When the `tile_cache_mb` setting is non-zero and the input DEM is a Whitebox raster that is too
+/// large to fit within the tile cache, the tool processes the DEM out of core, one band of rows at
+/// a time, such that DEMs larger than the available memory can be analyzed. The output must
+/// then also be a Whitebox raster.
+///
 /// # Reference
 /// Florinsky, I. (2016). Digital terrain analysis in soil science and geology. Academic Press.
 ///
@@ -58,7 +67,7 @@ impl Slope {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -168,74 +177,28 @@ impl WhiteboxTool for Slope {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
         let tool_name = self.get_tool_name();
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         // Read in the environment variables and get the necessary values
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
 
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut z_factor = -1f64;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let z_factor = parsed.get_f64("zfactor").unwrap_or(-1f64);
         let mut units_numeric = 1; // degrees
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                if keyval {
-                    input_file = vec[1].to_string();
-                } else {
-                    input_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-o" || flag_val == "-output" {
-                if keyval {
-                    output_file = vec[1].to_string();
-                } else {
-                    output_file = args[i + 1].to_string();
-                }
-            } else if flag_val == "-zfactor" {
-                if keyval {
-                    z_factor = vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                } else {
-                    z_factor = args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                }
-            } else if flag_val == "-units" {
-                let units = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-                units_numeric = if units.to_lowercase().contains("deg") {
-                    1
-                } else if units.contains("rad") {
-                    2
-                } else {
-                    3
-                };
-            }
+        if let Some(value) = parsed.get_string("units") {
+            let units = value.to_string();
+            units_numeric = if units.to_lowercase().contains("deg") {
+                1
+            } else if units.contains("rad") {
+                2
+            } else {
+                3
+            };
         }
 
         if verbose {
@@ -253,224 +216,48 @@ impl WhiteboxTool for Slope {
 
         let start = Instant::now();
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+
+        if TiledRaster::is_out_of_core_input(&input_file) {
+            return self.run_out_of_core(
+                &input_file,
+                &output_file,
+                z_factor,
+                units_numeric,
+                num_procs,
+                verbose,
+                reporter,
+            );
         }
 
         // Read in the input raster
         let input = Arc::new(Raster::new(&input_file, "r")?);
         let rows = input.configs.rows as isize;
-        let columns = input.configs.columns as isize;
         let nodata = input.configs.nodata;
         let resx = input.configs.resolution_x;
         let resy = input.configs.resolution_y;
         let res = (resx + resy) / 2.;
-        
-        let mut num_procs = num_cpus::get() as isize;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-        let (tx, rx) = mpsc::channel();
-        if !input.is_in_geographic_coordinates() {
-            for tid in 0..num_procs {
-                let input = input.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z12: f64;
-                    let mut p: f64;
-                    let mut q: f64;
-                    let offsets = [
-                        [-2, -2], [-1, -2], [0, -2], [1, -2], [2, -2], 
-                        [-2, -1], [-1, -1], [0, -1], [1, -1], [2, -1], 
-                        [-2, 0], [-1, 0], [0, 0], [1, 0], [2, 0], 
-                        [-2, 1], [-1, 1], [0, 1], [1, 1], [2, 1], 
-                        [-2, 2], [-1, 2], [0, 2], [1, 2], [2, 2]
-                    ];
-                    let mut z = [0f64; 25];
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            z12 = input.get_value(row, col);
-                            if z12 != nodata {
-                                for n in 0..25 {
-                                    z[n] = input.get_value(row + offsets[n][0], col + offsets[n][1]);
-                                    if z[n] != nodata {
-                                        z[n] *= z_factor;
-                                    } else {
-                                        z[n] = z12 * z_factor;
-                                    }
-                                }
-
-                                /* 
-                                The following equations have been taken from Florinsky (2016) Principles and Methods
-                                of Digital Terrain Modelling, Chapter 4, pg. 117. Note that I believe Florinsky reversed
-                                the equations for q and p.
-                                */
-                                q = 1. / (420. * res) * (44. * (z[3] + z[23] - z[1] - z[21]) + 31. * (z[0] + z[20] - z[4] - z[24]
-                                + 2. * (z[8] + z[18] - z[6] - z[16])) + 17. * (z[14] - z[10] + 4. * (z[13] - z[11]))
-                                + 5. * (z[9] + z[19] - z[5] - z[15]));
-
-                                p = 1. / (420. * res) * (44. * (z[5] + z[9] - z[15] - z[19]) + 31. * (z[20] + z[24] - z[0] - z[4]
-                                    + 2. * (z[6] + z[8] - z[16] - z[18])) + 17. * (z[2] - z[22] + 4. * (z[7] - z[17]))
-                                    + 5. * (z[1] + z[3] - z[21] - z[23]));
-
-                                /* 
-                                The following equation has been taken from Florinsky (2016) Principles and Methods
-                                of Digital Terrain Modelling, Chapter 2, pg. 18.
-                                */
-
-                                data[col as usize] = match units_numeric {
-                                    1 => (p * p + q * q).sqrt().atan().to_degrees(), // degrees
-                                    2 => (p * p + q * q).sqrt().atan(),              // radians
-                                    _ => (p * p + q * q).sqrt() * 100f64,            // percent
-                                };
-                            }
-                        }
-
-                        tx.send((row, data)).unwrap();
-                    }
-                });
-            }
-        } else { // geographic coordinates
-
-            let phi1 = input.get_y_from_row(0);
-            let lambda1 = input.get_x_from_column(0);
-
-            let phi2 = phi1;
-            let lambda2 = input.get_x_from_column(-1);
-
-            let linear_res = vincenty_distance((phi1, lambda1), (phi2, lambda2));
-            let lr2 =  haversine_distance((phi1, lambda1), (phi2, lambda2)); 
-            let diff = 100. * (linear_res - lr2).abs() / linear_res;
-            let use_haversine = diff < 0.5; // if the difference is less than 0.5%, use the faster haversine method to calculate distances.
-
-            for tid in 0..num_procs {
-                let input = input.clone();
-                let tx = tx.clone();
-                thread::spawn(move || {
-                    let mut z4: f64;
-                    let mut p: f64;
-                    let mut q: f64;
-                    let mut a: f64;
-                    let mut b: f64;
-                    let mut c: f64;
-                    let mut d: f64;
-                    let mut e: f64;
-                    let mut phi1: f64;
-                    let mut lambda1: f64;
-                    let mut phi2: f64;
-                    let mut lambda2: f64;
-                    let offsets = [
-                        [-1, -1], [0, -1], [1, -1], 
-                        [-1, 0], [0, 0], [1, 0], 
-                        [-1, 1], [0, 1], [1, 1]
-                    ];
-                    let mut z = [0f64; 25];
-                    for row in (0..rows).filter(|r| r % num_procs == tid) {
-                        let mut data = vec![nodata; columns as usize];
-                        for col in 0..columns {
-                            z4 = input.get_value(row, col);
-                            if z4 != nodata {
-                                for n in 0..9 {
-                                    z[n] = input.get_value(row + offsets[n][1], col + offsets[n][0]);
-                                    if z[n] != nodata {
-                                        z[n] *= z_factor;
-                                    } else {
-                                        z[n] = z4 * z_factor;
-                                    }
-                                }
-
-                                // Calculate a, b, c, d, and e.
-                                phi1 = input.get_y_from_row(row);
-                                lambda1 = input.get_x_from_column(col);
-
-                                phi2 = phi1;
-                                lambda2 = input.get_x_from_column(col-1);
-
-                                b = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
-
-                                phi2 = input.get_y_from_row(row+1);
-                                lambda2 = lambda1;
-
-                                d = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
-
-                                phi2 = input.get_y_from_row(row-1);
-                                lambda2 = lambda1;
-
-                                e = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
-
-                                phi1 = input.get_y_from_row(row+1);
-                                lambda1 = input.get_x_from_column(col);
-
-                                phi2 = phi1;
-                                lambda2 = input.get_x_from_column(col-1);
-
-                                a = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
-
-                                phi1 = input.get_y_from_row(row-1);
-                                lambda1 = input.get_x_from_column(col);
-
-                                phi2 = phi1;
-                                lambda2 = input.get_x_from_column(col-1);
-
-                                c = if use_haversine {
-                                    haversine_distance((phi1, lambda1), (phi2, lambda2))
-                                } else {
-                                    vincenty_distance((phi1, lambda1), (phi2, lambda2))
-                                };
-
-                                /* 
-                                The following equations have been taken from Florinsky (2016) Principles and Methods
-                                of Digital Terrain Modelling, Chapter 4, pg. 117.
-                                */
-
-                                p = (a * a * c * d * (d + e) * (z[2] - z[0]) + b * (a * a * d * d + c * c * e * e) * (z[5] - z[3]) + a * c * c * e * (d + e) * (z[8] - z[6]))
-                                / (2. * (a * a * c * c * (d + e).powi(2) + b * b * (a * a * d * d + c * c * e * e)));
-
-                                q = 1. / (3. * d * e * (d + e) * (a.powi(4) + b.powi(4) + c.powi(4))) 
-                                * ((d * d * (a.powi(4) + b.powi(4) + b * b * c * c) + c * c * e * e * (a * a - b * b)) * (z[0] + z[2])
-                                - (d * d * (a.powi(4) + c.powi(4) + b * b * c * c) - e * e * (a.powi(4) + c.powi(4) + a * a * b * b)) * (z[3] + z[5])
-                                - (e * e * (b.powi(4) + c.powi(4) + a * a * b * b) - a * a * d * d * (b * b - c * c)) * (z[6] + z[8])
-                                + d * d * (b.powi(4) * (z[1] - 3. * z[4]) + c.powi(4) * (3. * z[1] - z[4]) + (a.powi(4) - 2. * b * b * c * c) * (z[1] - z[4]))
-                                + e * e * (a.powi(4) * (z[4] - 3. * z[7]) + b.powi(4) * (3. * z[4] - z[7]) + (c.powi(4) - 2. * a * a * b * b) * (z[4] - z[7]))
-                                - 2. * (a * a * d * d * (b * b - c * c) * z[7] + c * c * e * e * (a * a - b * b) * z[1]));
-
-                                /* 
-                                The following equation has been taken from Florinsky (2016) Principles and Methods
-                                of Digital Terrain Modelling, Chapter 2, pg. 18.
-                                */
-
-                                data[col as usize] = match units_numeric {
-                                    1 => (p * p + q * q).sqrt().atan().to_degrees(), // degrees
-                                    2 => (p * p + q * q).sqrt().atan(),              // radians
-                                    _ => (p * p + q * q).sqrt() * 100f64,            // percent
-                                };
-                            }
-                        }
+        let gpu_rows = if gpu::gpu_requested() && !input.is_in_geographic_coordinates() {
+            let kernel = gpu::FocalKernel::Slope {
+                z_factor: z_factor,
+                res: res,
+                units: units_numeric as u32,
+            };
+            gpu::run_focal(&input, &kernel, nodata, verbose)
+        } else {
+            None
+        };
 
-                        tx.send((row, data)).unwrap();
-                    }
-                });
+        let (tx, rx) = mpsc::channel();
+        if let Some(gpu_rows) = gpu_rows {
+            for (row, data) in gpu_rows.into_iter().enumerate() {
+                tx.send((row as isize, data)).unwrap();
             }
+        } else {
+            spawn_slope_workers(&input, 0..rows, z_factor, units_numeric, num_procs, &tx);
         }
 
         let mut output = Raster::initialize_using_file(&output_file, &input);
@@ -478,16 +265,16 @@ impl WhiteboxTool for Slope {
         for row in 0..rows {
             let (r, data) = rx.recv().expect("Error receiving data from thread.");
             output.set_row_data(r, data);
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress: {}%", progress);
+                    reporter.progress("Progress", progress);
                     old_progress = progress;
                 }
             }
         }
 
-        
         //////////////////////
         // Output the image //
         //////////////////////
@@ -523,3 +310,302 @@ impl WhiteboxTool for Slope {
         Ok(())
     }
 }
+
+impl Slope {
+    /// Calculates the slope of a DEM that is too large to read into memory, one band of rows
+    /// at a time.
+    fn run_out_of_core(
+        &self,
+        input_file: &str,
+        output_file: &str,
+        z_factor: f64,
+        units_numeric: i32,
+        num_procs: isize,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        if verbose {
+            println!("Processing the input raster out of core...")
+        };
+        let mut input = TiledRaster::open(input_file)?;
+        let start = Instant::now();
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns;
+
+        let mut output = TiledRaster::initialize_using_config(output_file, &input.configs)?;
+        output.configs.data_type = DataType::F32;
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+        // the bands are padded by the radius of the largest (5x5) neighbourhood
+        let pad = 2isize;
+        let band_rows = input.tile_size as isize;
+        let mut band_start = 0isize;
+        let mut rows_completed = 0isize;
+        while band_start < rows {
+            let n = band_rows.min(rows - band_start);
+            let band = Arc::new(input.read_rows(band_start - pad, (n + 2 * pad) as usize)?);
+            let (tx, rx) = mpsc::channel();
+            spawn_slope_workers(&band, pad..n + pad, z_factor, units_numeric, num_procs, &tx);
+            let mut data = vec![0f64; n as usize * columns];
+            for _ in 0..n {
+                let (row, values) = rx.recv().expect("Error receiving data from thread.");
+                let offset = (row - pad) as usize * columns;
+                data[offset..offset + columns].copy_from_slice(&values);
+
+                reporter.check_cancelled()?;
+                rows_completed += 1;
+                if verbose {
+                    progress = (100.0_f64 * rows_completed as f64 / (rows - 1).max(1) as f64) as usize;
+                    if progress != old_progress {
+                        reporter.progress("Progress", progress);
+                        old_progress = progress;
+                    }
+                }
+            }
+            output.set_rows(band_start, &data)?;
+            band_start += n;
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        output.write()?;
+        if verbose {
+            println!("Output file written");
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Spawns threads that calculate the slope of the cells in `rows` of `input`, sending each row
+/// of output values, along with its row number, to `tx`.
+fn spawn_slope_workers(
+    input: &Arc<Raster>,
+    rows: Range<isize>,
+    z_factor: f64,
+    units_numeric: i32,
+    num_procs: isize,
+    tx: &mpsc::Sender<(isize, Vec<f64>)>,
+) {
+    let columns = input.configs.columns as isize;
+    let nodata = input.configs.nodata;
+    let res = (input.configs.resolution_x + input.configs.resolution_y) / 2.;
+    if !input.is_in_geographic_coordinates() {
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let rows = rows.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let mut z12: f64;
+                let mut p: f64;
+                let mut q: f64;
+                let offsets = [
+                    [-2, -2], [-1, -2], [0, -2], [1, -2], [2, -2], 
+                    [-2, -1], [-1, -1], [0, -1], [1, -1], [2, -1], 
+                    [-2, 0], [-1, 0], [0, 0], [1, 0], [2, 0], 
+                    [-2, 1], [-1, 1], [0, 1], [1, 1], [2, 1], 
+                    [-2, 2], [-1, 2], [0, 2], [1, 2], [2, 2]
+                ];
+                let mut z = [0f64; 25];
+                for row in rows.filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z12 = input.get_value(row, col);
+                        if !is_nodata(z12, nodata) {
+                            for n in 0..25 {
+                                z[n] = input.get_value(row + offsets[n][0], col + offsets[n][1]);
+                                if !is_nodata(z[n], nodata) {
+                                    z[n] *= z_factor;
+                                } else {
+                                    z[n] = z12 * z_factor;
+                                }
+                            }
+
+                            /* 
+                            The following equations have been taken from Florinsky (2016) Principles and Methods
+                            of Digital Terrain Modelling, Chapter 4, pg. 117. Note that I believe Florinsky reversed
+                            the equations for q and p.
+                            */
+                            q = 1. / (420. * res) * (44. * (z[3] + z[23] - z[1] - z[21]) + 31. * (z[0] + z[20] - z[4] - z[24]
+                            + 2. * (z[8] + z[18] - z[6] - z[16])) + 17. * (z[14] - z[10] + 4. * (z[13] - z[11]))
+                            + 5. * (z[9] + z[19] - z[5] - z[15]));
+
+                            p = 1. / (420. * res) * (44. * (z[5] + z[9] - z[15] - z[19]) + 31. * (z[20] + z[24] - z[0] - z[4]
+                                + 2. * (z[6] + z[8] - z[16] - z[18])) + 17. * (z[2] - z[22] + 4. * (z[7] - z[17]))
+                                + 5. * (z[1] + z[3] - z[21] - z[23]));
+
+                            /* 
+                            The following equation has been taken from Florinsky (2016) Principles and Methods
+                            of Digital Terrain Modelling, Chapter 2, pg. 18.
+                            */
+
+                            data[col as usize] = match units_numeric {
+                                1 => (p * p + q * q).sqrt().atan().to_degrees(), // degrees
+                                2 => (p * p + q * q).sqrt().atan(),              // radians
+                                _ => (p * p + q * q).sqrt() * 100f64,            // percent
+                            };
+                        }
+                    }
+
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    } else { // geographic coordinates
+
+        let phi1 = input.get_y_from_row(0);
+        let lambda1 = input.get_x_from_column(0);
+
+        let phi2 = phi1;
+        let lambda2 = input.get_x_from_column(-1);
+
+        let linear_res = vincenty_distance((phi1, lambda1), (phi2, lambda2));
+        let lr2 =  haversine_distance((phi1, lambda1), (phi2, lambda2)); 
+        let diff = 100. * (linear_res - lr2).abs() / linear_res;
+        let use_haversine = diff < 0.5; // if the difference is less than 0.5%, use the faster haversine method to calculate distances.
+
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let rows = rows.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let mut z4: f64;
+                let mut p: f64;
+                let mut q: f64;
+                let mut a: f64;
+                let mut b: f64;
+                let mut c: f64;
+                let mut d: f64;
+                let mut e: f64;
+                let mut phi1: f64;
+                let mut lambda1: f64;
+                let mut phi2: f64;
+                let mut lambda2: f64;
+                let offsets = [
+                    [-1, -1], [0, -1], [1, -1], 
+                    [-1, 0], [0, 0], [1, 0], 
+                    [-1, 1], [0, 1], [1, 1]
+                ];
+                let mut z = [0f64; 25];
+                for row in rows.filter(|r| r % num_procs == tid) {
+                    let mut data = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z4 = input.get_value(row, col);
+                        if !is_nodata(z4, nodata) {
+                            for n in 0..9 {
+                                z[n] = input.get_value(row + offsets[n][1], col + offsets[n][0]);
+                                if !is_nodata(z[n], nodata) {
+                                    z[n] *= z_factor;
+                                } else {
+                                    z[n] = z4 * z_factor;
+                                }
+                            }
+
+                            // Calculate a, b, c, d, and e.
+                            phi1 = input.get_y_from_row(row);
+                            lambda1 = input.get_x_from_column(col);
+
+                            phi2 = phi1;
+                            lambda2 = input.get_x_from_column(col-1);
+
+                            b = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi2 = input.get_y_from_row(row+1);
+                            lambda2 = lambda1;
+
+                            d = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi2 = input.get_y_from_row(row-1);
+                            lambda2 = lambda1;
+
+                            e = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi1 = input.get_y_from_row(row+1);
+                            lambda1 = input.get_x_from_column(col);
+
+                            phi2 = phi1;
+                            lambda2 = input.get_x_from_column(col-1);
+
+                            a = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            phi1 = input.get_y_from_row(row-1);
+                            lambda1 = input.get_x_from_column(col);
+
+                            phi2 = phi1;
+                            lambda2 = input.get_x_from_column(col-1);
+
+                            c = if use_haversine {
+                                haversine_distance((phi1, lambda1), (phi2, lambda2))
+                            } else {
+                                vincenty_distance((phi1, lambda1), (phi2, lambda2))
+                            };
+
+                            /* 
+                            The following equations have been taken from Florinsky (2016) Principles and Methods
+                            of Digital Terrain Modelling, Chapter 4, pg. 117.
+                            */
+
+                            p = (a * a * c * d * (d + e) * (z[2] - z[0]) + b * (a * a * d * d + c * c * e * e) * (z[5] - z[3]) + a * c * c * e * (d + e) * (z[8] - z[6]))
+                            / (2. * (a * a * c * c * (d + e).powi(2) + b * b * (a * a * d * d + c * c * e * e)));
+
+                            q = 1. / (3. * d * e * (d + e) * (a.powi(4) + b.powi(4) + c.powi(4))) 
+                            * ((d * d * (a.powi(4) + b.powi(4) + b * b * c * c) + c * c * e * e * (a * a - b * b)) * (z[0] + z[2])
+                            - (d * d * (a.powi(4) + c.powi(4) + b * b * c * c) - e * e * (a.powi(4) + c.powi(4) + a * a * b * b)) * (z[3] + z[5])
+                            - (e * e * (b.powi(4) + c.powi(4) + a * a * b * b) - a * a * d * d * (b * b - c * c)) * (z[6] + z[8])
+                            + d * d * (b.powi(4) * (z[1] - 3. * z[4]) + c.powi(4) * (3. * z[1] - z[4]) + (a.powi(4) - 2. * b * b * c * c) * (z[1] - z[4]))
+                            + e * e * (a.powi(4) * (z[4] - 3. * z[7]) + b.powi(4) * (3. * z[4] - z[7]) + (c.powi(4) - 2. * a * a * b * b) * (z[4] - z[7]))
+                            - 2. * (a * a * d * d * (b * b - c * c) * z[7] + c * c * e * e * (a * a - b * b) * z[1]));
+
+                            /* 
+                            The following equation has been taken from Florinsky (2016) Principles and Methods
+                            of Digital Terrain Modelling, Chapter 2, pg. 18.
+                            */
+
+                            data[col as usize] = match units_numeric {
+                                1 => (p * p + q * q).sqrt().atan().to_degrees(), // degrees
+                                2 => (p * p + q * q).sqrt().atan(),              // radians
+                                _ => (p * p + q * q).sqrt() * 100f64,            // percent
+                            };
+                        }
+                    }
+
+                    if tx.send((row, data)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    }
+}
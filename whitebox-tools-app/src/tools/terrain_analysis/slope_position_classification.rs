@@ -0,0 +1,599 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+Notes: As with PennockLandformClass, some degree of DEM smoothing is likely required to get reasonable results.
+*/
+
+use super::horizon_search::ground_cell_sizes;
+use whitebox_raster::*;
+use whitebox_common::structures::Array2D;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+
+/// This tool classifies each grid cell in a digital elevation model (`--dem`) into one of six hillslope
+/// position elements: summit, shoulder, backslope, footslope, toeslope, and flat (Ruhe, 1960; Schoeneberger
+/// and Wysocki, 2005). The classification combines two sources of information: the relative position of a cell
+/// along its hillslope, and the local slope gradient and profile curvature. The tool also optionally outputs
+/// the hillslope length (`--out_length`), i.e. the length of the longest flowpath from a drainage divide to
+/// each cell. Both outputs are commonly used as inputs to soil-landscape and erosion models (e.g. the
+/// slope-length factor of the USLE/RUSLE).
+///
+/// Hillslope position is measured along D8 flowpaths. The relative slope position (RP) of a cell is:
+///
+/// > RP = L<sub>up</sub> / (L<sub>up</sub> + L<sub>down</sub>)
+///
+/// where L<sub>up</sub> is the hillslope length, the longest upslope flowpath from the divide to the cell, and
+/// L<sub>down</sub> is the downslope flowpath length from the cell to the nearest stream cell, or to the edge of
+/// the DEM where streams are not specified. RP is therefore zero at the divide and approaches one at the base of
+/// the hillslope. An optional streams raster (`--streams`), in which stream cells have positive, non-zero values,
+/// terminates hillslopes at the channel network; hillslope lengths are not accumulated across streams and stream
+/// cells are assigned the nodata value in both outputs.
+///
+/// Cells are classified using the following rules, applied in order:
+///
+/// | Slope              | RP                      | Profile curvature     | Element        | Code |
+/// |:-------------------|:------------------------|:----------------------|:---------------|:---- |
+/// | ≤ `--slope`        | ≤ `--upper_rp`          | --                    | Summit         | 1    |
+/// | ≤ `--slope`        | ≥ `--lower_rp`          | --                    | Toeslope       | 5    |
+/// | ≤ `--slope`        | --                      | --                    | Flat           | 6    |
+/// | > `--slope`        | ≤ `--upper_rp`          | not concave           | Shoulder       | 2    |
+/// | > `--slope`        | ≥ `--lower_rp`          | not convex            | Footslope      | 4    |
+/// | > `--slope`        | --                      | convex (> `--prof`)   | Shoulder       | 2    |
+/// | > `--slope`        | --                      | concave (< -`--prof`) | Footslope      | 4    |
+/// | > `--slope`        | --                      | linear                | Backslope      | 3    |
+///
+/// Here `--upper_rp` (default 0.15) is the relative position below which a cell is considered to be near the
+/// top of its hillslope, and `--lower_rp` (default 0.85) is the relative position above which a cell is
+/// considered to be near the base. Slope is measured in degrees and profile curvature is calculated, and
+/// thresholded (`--prof`), in the same way as in the `PennockLandformClass` tool. All thresholds are likely to be
+/// landscape and data specific and may need to be adjusted by the user. The DEM should be hydrologically
+/// conditioned (e.g. using `BreachDepressions`) so that flowpaths are continuous, and a smoothing filter, such
+/// as `FeaturePreservingSmoothing`, can reduce the sensitivity of the curvature-based elements to surface roughness.
+///
+/// # References
+/// Ruhe, R. V. (1960). Elements of the soil landscape. *Transactions of the 7th International Congress of Soil
+/// Science*, 4, 165-170.
+///
+/// Schoeneberger, P. J., and Wysocki, D. A. (2005). Hydrology of soils and deep regolith: a nexus between soil
+/// geography, ecosystems and land management. *Geoderma*, 126(1-2), 117-128.
+///
+/// # See Also
+/// `PennockLandformClass`, `MaxUpslopeFlowpathLength`, `DownslopeDistanceToStream`, `BreachDepressions`,
+/// `FeaturePreservingSmoothing`
+pub struct SlopePositionClassification {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SlopePositionClassification {
+    pub fn new() -> SlopePositionClassification {
+        // public constructor
+        let name = "SlopePositionClassification".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Classifies a DEM into hillslope position elements and calculates hillslope length.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Streams File (optional)".to_owned(),
+            flags: vec!["--streams".to_owned()],
+            description: "Input raster streams file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output slope position class raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Hillslope Length File (optional)".to_owned(),
+            flags: vec!["--out_length".to_owned()],
+            description: "Output hillslope length raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Slope Threshold (degrees)".to_owned(),
+            flags: vec!["--slope".to_owned()],
+            description: "Slope threshold value, in degrees, below which terrain is considered level.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("3.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Profile Curvature Threshold".to_owned(),
+            flags: vec!["--prof".to_owned()],
+            description: "Profile curvature threshold value.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Upper Relative Position Threshold".to_owned(),
+            flags: vec!["--upper_rp".to_owned()],
+            description: "Relative slope position (0-1) below which cells are near the top of the hillslope.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.15".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Lower Relative Position Threshold".to_owned(),
+            flags: vec!["--lower_rp".to_owned()],
+            description: "Relative slope position (0-1) above which cells are near the base of the hillslope.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.85".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(
+            ">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --streams=streams.tif -o=slope_position.tif --out_length=hillslope_length.tif --slope=3.0 --prof=0.1 --upper_rp=0.15 --lower_rp=0.85",
+            short_exe, name
+        )
+        .replace("*", &sep);
+
+        SlopePositionClassification {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SlopePositionClassification {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut streams_file = parsed.get_string("streams").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut length_file = parsed.get_string("out_length").unwrap_or_default();
+        let slope_threshold = parsed.get_f64("slope").unwrap_or(3f64);
+        let prof_threshold = parsed.get_f64("prof").unwrap_or(0.1f64);
+        let upper_rp = parsed.get_f64("upper_rp").unwrap_or(0.15f64);
+        let lower_rp = parsed.get_f64("lower_rp").unwrap_or(0.85f64);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        streams_file = resolve_input_path(&streams_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
+        length_file = resolve_path(&length_file, working_directory);
+        if upper_rp < 0f64 || lower_rp > 1f64 || upper_rp >= lower_rp {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The relative position thresholds must satisfy 0 <= --upper_rp < --lower_rp <= 1.",
+            ));
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let input = Arc::new(Raster::new(&input_file, "r")?);
+        let rows = input.configs.rows as isize;
+        let columns = input.configs.columns as isize;
+        let nodata = input.configs.nodata;
+
+        let mut is_stream: Array2D<u8> = Array2D::new(rows, columns, 0, 0)?;
+        if !streams_file.is_empty() {
+            let streams = Raster::new(&streams_file, "r")?;
+            if streams.configs.rows as isize != rows || streams.configs.columns as isize != columns {
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
+            }
+            let streams_nodata = streams.configs.nodata;
+            let mut value: f64;
+            for row in 0..rows {
+                reporter.check_cancelled()?;
+                for col in 0..columns {
+                    value = streams.get_value(row, col);
+                    if !is_nodata(value, streams_nodata) && value > 0f64 {
+                        is_stream.set_value(row, col, 1);
+                    }
+                }
+            }
+        }
+
+        let start = Instant::now();
+
+        let (cell_size_x, cell_size_y) = ground_cell_sizes(&input);
+        let diag_cell_size = cell_size_x.hypot(cell_size_y);
+
+        let configs = whitebox_common::configs::get_configs()?;
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
+
+        // Calculate the D8 flow directions, slope gradient, and profile curvature.
+        let (tx, rx) = mpsc::channel();
+        for tid in 0..num_procs {
+            let input = input.clone();
+            let tx = tx.clone();
+            spawn(move || {
+                let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+                let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+                let grid_lengths = [
+                    diag_cell_size,
+                    cell_size_x,
+                    diag_cell_size,
+                    cell_size_y,
+                    diag_cell_size,
+                    cell_size_x,
+                    diag_cell_size,
+                    cell_size_y,
+                ];
+                let mut n = [0f64; 8];
+                let (mut z, mut max_slope, mut s): (f64, f64, f64);
+                let (mut zx, mut zy, mut zxx, mut zyy, mut zxy, mut p): (f64, f64, f64, f64, f64, f64);
+                let (mut fx, mut fy): (f64, f64);
+                let mut dir: i8;
+                for row in (0..rows).filter(|r| r % num_procs == tid) {
+                    let mut dirs = vec![-1i8; columns as usize];
+                    let mut slopes = vec![nodata; columns as usize];
+                    let mut profs = vec![nodata; columns as usize];
+                    for col in 0..columns {
+                        z = input.get_value(row, col);
+                        if is_nodata(z, nodata) {
+                            continue;
+                        }
+                        dir = -1i8;
+                        max_slope = 0f64;
+                        for i in 0..8 {
+                            n[i] = input.get_value(row + d_y[i], col + d_x[i]);
+                            if !is_nodata(n[i], nodata) {
+                                s = (z - n[i]) / grid_lengths[i];
+                                if s > max_slope {
+                                    max_slope = s;
+                                    dir = i as i8;
+                                }
+                            } else {
+                                n[i] = z;
+                            }
+                        }
+                        dirs[col as usize] = dir;
+
+                        fx = (n[2] - n[4] + 2.0 * (n[1] - n[5]) + n[0] - n[6]) / (8.0 * cell_size_x);
+                        fy = (n[6] - n[4] + 2.0 * (n[7] - n[3]) + n[0] - n[2]) / (8.0 * cell_size_y);
+                        slopes[col as usize] = (fx * fx + fy * fy).sqrt().atan().to_degrees();
+
+                        zx = (n[1] - n[5]) / (2.0 * cell_size_x);
+                        zy = (n[7] - n[3]) / (2.0 * cell_size_y);
+                        zxx = (n[1] - 2.0 * z + n[5]) / (cell_size_x * cell_size_x);
+                        zyy = (n[7] - 2.0 * z + n[3]) / (cell_size_y * cell_size_y);
+                        zxy = (-n[6] + n[0] + n[4] - n[2]) / (4.0 * cell_size_x * cell_size_y);
+                        p = zx * zx + zy * zy;
+                        profs[col as usize] = if p > 0f64 {
+                            -1f64
+                                * ((zxx * zx * zx - 2.0 * zxy * zx * zy + zyy * zy * zy)
+                                    / (p * (p + 1.0).powf(1.5)))
+                                .to_degrees()
+                        } else {
+                            0f64
+                        };
+                    }
+                    if tx.send((row, dirs, slopes, profs)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        let mut flow_dir: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
+        let mut slope: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        let mut prof: Array2D<f64> = Array2D::new(rows, columns, nodata, nodata)?;
+        for r in 0..rows {
+            let (row, dirs, slopes, profs) = rx.recv().expect("Error receiving data from thread.");
+            flow_dir.set_row_data(row, dirs);
+            slope.set_row_data(row, slopes);
+            prof.set_row_data(row, profs);
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Calculating terrain attributes", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        // Hillslope length is the longest upslope flowpath, which is found by processing the cells
+        // in topological order, from the divides downslope. Stream cells restart the accumulation.
+        let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
+        let d_y = [-1, 0, 1, 1, 1, 0, -1, -1];
+        let inflowing_vals: [i8; 8] = [4, 5, 6, 7, 0, 1, 2, 3];
+        let grid_lengths = [
+            diag_cell_size,
+            cell_size_x,
+            diag_cell_size,
+            cell_size_y,
+            diag_cell_size,
+            cell_size_x,
+            diag_cell_size,
+            cell_size_y,
+        ];
+        let mut num_inflowing: Array2D<i8> = Array2D::new(rows, columns, -1, -1)?;
+        let mut up_length: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+        let mut stack = vec![];
+        let mut count: i8;
+        for row in 0..rows {
+            reporter.check_cancelled()?;
+            for col in 0..columns {
+                if is_nodata(input.get_value(row, col), nodata) {
+                    continue;
+                }
+                count = 0;
+                for i in 0..8 {
+                    if flow_dir.get_value(row + d_y[i], col + d_x[i]) == inflowing_vals[i] {
+                        count += 1;
+                    }
+                }
+                num_inflowing.set_value(row, col, count);
+                if count == 0 {
+                    stack.push((row, col));
+                }
+            }
+        }
+
+        let mut order = Vec::with_capacity((rows * columns) as usize);
+        let (mut row_n, mut col_n): (isize, isize);
+        let mut dir: i8;
+        let mut length: f64;
+        while let Some((row, col)) = stack.pop() {
+            reporter.check_cancelled()?;
+            order.push((row, col));
+            if is_stream.get_value(row, col) == 1 {
+                up_length.set_value(row, col, 0f64);
+            }
+            dir = flow_dir.get_value(row, col);
+            if dir >= 0 {
+                row_n = row + d_y[dir as usize];
+                col_n = col + d_x[dir as usize];
+                length = up_length.get_value(row, col) + grid_lengths[dir as usize];
+                if length > up_length.get_value(row_n, col_n) {
+                    up_length.set_value(row_n, col_n, length);
+                }
+                num_inflowing.decrement(row_n, col_n, 1i8);
+                if num_inflowing.get_value(row_n, col_n) == 0i8 {
+                    stack.push((row_n, col_n));
+                }
+            }
+        }
+
+        // The downslope length to the nearest stream (or outlet) is found by processing the cells
+        // in the reverse order.
+        let mut down_length: Array2D<f64> = Array2D::new(rows, columns, 0f64, nodata)?;
+        for &(row, col) in order.iter().rev() {
+            dir = flow_dir.get_value(row, col);
+            if dir >= 0 && is_stream.get_value(row, col) == 0 {
+                row_n = row + d_y[dir as usize];
+                col_n = col + d_x[dir as usize];
+                down_length.set_value(
+                    row,
+                    col,
+                    down_length.get_value(row_n, col_n) + grid_lengths[dir as usize],
+                );
+            }
+        }
+
+        let mut output = Raster::initialize_using_file(&output_file, &input);
+        output.configs.nodata = -128f64;
+        output.configs.data_type = DataType::I8;
+        output.configs.photometric_interp = PhotometricInterpretation::Categorical;
+        output.reinitialize_values(-128f64);
+        let mut lengths = if !length_file.is_empty() {
+            let mut r = Raster::initialize_using_file(&length_file, &input);
+            r.configs.data_type = DataType::F32;
+            r.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            r.configs.palette = "blueyellow.plt".to_string();
+            r.configs.palette_nonlinearity = 0.3f64;
+            r.reinitialize_values(nodata);
+            Some(r)
+        } else {
+            None
+        };
+        let (mut up, mut down, mut rp, mut s, mut pc): (f64, f64, f64, f64, f64);
+        let mut class: f64;
+        for row in 0..rows {
+            for col in 0..columns {
+                if is_nodata(input.get_value(row, col), nodata) || is_stream.get_value(row, col) == 1 {
+                    continue;
+                }
+                up = up_length.get_value(row, col);
+                down = down_length.get_value(row, col);
+                rp = if up + down > 0f64 { up / (up + down) } else { 0.5 };
+                s = slope.get_value(row, col);
+                pc = prof.get_value(row, col);
+                class = if s <= slope_threshold {
+                    if rp <= upper_rp {
+                        1f64 // summit
+                    } else if rp >= lower_rp {
+                        5f64 // toeslope
+                    } else {
+                        6f64 // flat
+                    }
+                } else if rp <= upper_rp && pc >= -prof_threshold {
+                    2f64 // shoulder
+                } else if rp >= lower_rp && pc <= prof_threshold {
+                    4f64 // footslope
+                } else if pc > prof_threshold {
+                    2f64 // shoulder
+                } else if pc < -prof_threshold {
+                    4f64 // footslope
+                } else {
+                    3f64 // backslope
+                };
+                output.set_value(row, col, class);
+                if let Some(ref mut r) = lengths {
+                    r.set_value(row, col, up);
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Classifying", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        output.add_metadata_entry(format!(
+            "Created by whitebox_tools\' {} tool",
+            self.get_tool_name()
+        ));
+        output.add_metadata_entry(format!("Input file: {}", input_file));
+        if !streams_file.is_empty() {
+            output.add_metadata_entry(format!("Streams file: {}", streams_file));
+        }
+        output.add_metadata_entry(format!("Slope threshold: {}", slope_threshold));
+        output.add_metadata_entry(format!("Profile curvature threshold: {}", prof_threshold));
+        output.add_metadata_entry(format!("Upper relative position threshold: {}", upper_rp));
+        output.add_metadata_entry(format!("Lower relative position threshold: {}", lower_rp));
+        output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+        output.add_metadata_entry(format!("CLASSIFICATION KEY"));
+        output.add_metadata_entry(format!("Value  Class"));
+        output.add_metadata_entry(format!("1      Summit"));
+        output.add_metadata_entry(format!("2      Shoulder"));
+        output.add_metadata_entry(format!("3      Backslope"));
+        output.add_metadata_entry(format!("4      Footslope"));
+        output.add_metadata_entry(format!("5      Toeslope"));
+        output.add_metadata_entry(format!("6      Flat"));
+
+        if verbose {
+            println!("Saving data...")
+        };
+        let _ = match output.write() {
+            Ok(_) => {
+                if verbose {
+                    println!("Output file written")
+                }
+            }
+            Err(e) => return Err(e),
+        };
+
+        if let Some(mut r) = lengths {
+            r.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            r.add_metadata_entry(format!("Input file: {}", input_file));
+            r.add_metadata_entry(format!("Hillslope length"));
+            r.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+            let _ = match r.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Hillslope length file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!("CLASSIFICATION KEY");
+            println!("Value  Class");
+            println!("1      Summit");
+            println!("2      Shoulder");
+            println!("3      Backslope");
+            println!("4      Footslope");
+            println!("5      Toeslope");
+            println!("6      Flat");
+
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -6,12 +6,11 @@ Last Modified: 03/09/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::rendering::html::*;
 use whitebox_common::rendering::LineGraph;
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::fs::File;
@@ -22,7 +21,7 @@ use std::path;
 use std::process::Command;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to create a slope versus average elevation plot for one or more digital elevation models (DEMs).
 /// Similar to a hypsometric analysis (`HypsometricAnalysis`), the slope-elevation relation can reveal the basic
@@ -150,47 +149,12 @@ impl WhiteboxTool for SlopeVsElevationPlot {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_files_str = String::new();
-        let mut watershed_files_str = "".to_string();
-        let mut output_file = String::new();
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-inputs" {
-                input_files_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-watershed" {
-                watershed_files_str = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let input_files_str = parsed.get_string("inputs").unwrap_or_default();
+        let watershed_files_str = parsed.get_string("watershed").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -203,8 +167,6 @@ impl WhiteboxTool for SlopeVsElevationPlot {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
@@ -222,9 +184,7 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                                 "There is something incorrect about the input DEM files. At least one input DEM is required to operate this tool."));
         }
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
         if !output_file.ends_with(".html") {
             output_file.push_str(".html");
         }
@@ -258,9 +218,7 @@ impl WhiteboxTool for SlopeVsElevationPlot {
         if watershed_files_str.is_empty() {
             for i in 0..num_files {
                 let mut input_file = input_files[i].to_string();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
 
                 if verbose {
                     println!("Reading data...");
@@ -292,17 +250,13 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                 }
 
                 // calculate slope
-                let mut num_procs = num_cpus::get() as isize;
                 let configs = whitebox_common::configs::get_configs()?;
-                let max_procs = configs.max_procs;
-                if max_procs > 0 && max_procs < num_procs {
-                    num_procs = max_procs;
-                }
+                let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
                 let (tx, rx) = mpsc::channel();
                 for tid in 0..num_procs {
                     let input = input.clone();
                     let tx1 = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         let nodata = input.configs.nodata;
                         let columns = input.configs.columns as isize;
                         let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
@@ -314,10 +268,10 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 z = input[(row, col)];
-                                if z != nodata {
+                                if !is_nodata(z, nodata) {
                                     for c in 0..8 {
                                         n[c] = input[(row + d_y[c], col + d_x[c])];
-                                        if n[c] != nodata {
+                                        if !is_nodata(n[c], nodata) {
                                             n[c] = n[c] * z_factor;
                                         } else {
                                             n[c] = z * z_factor;
@@ -332,7 +286,9 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                                         (fx * fx + fy * fy).sqrt().atan().to_degrees();
                                 }
                             }
-                            tx1.send((row, data)).unwrap();
+                            if tx1.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -342,10 +298,11 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                     let data = rx.recv().expect("Error receiving data from thread.");
                     slope.set_row_data(data.0, data.1);
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Slope analysis: {}%", progress);
+                            reporter.progress("Slope analysis", progress);
                             old_progress = progress;
                         }
                     }
@@ -367,16 +324,17 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                 for row in 0..rows {
                     for col in 0..columns {
                         val = input.get_value(row, col);
-                        if val != nodata {
+                        if !is_nodata(val, nodata) {
                             bin = ((val - min) / bin_width).floor() as usize;
                             freq_data[bin] += 1f64;
                             slope_data[bin] += slope.get_value(row, col);
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Binning the data: {}%", progress);
+                            reporter.progress("Binning the data", progress);
                             old_progress = progress;
                         }
                     }
@@ -412,9 +370,7 @@ impl WhiteboxTool for SlopeVsElevationPlot {
 
             for i in 0..num_files {
                 let mut input_file = input_files[i].to_string();
-                if !input_file.contains(&sep) && !input_file.contains("/") {
-                    input_file = format!("{}{}", working_directory, input_file);
-                }
+                input_file = resolve_input_path(&input_file, working_directory)?;
                 if verbose {
                     println!("Reading data...");
                 }
@@ -444,17 +400,13 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                 }
 
                 // calculate slope
-                let mut num_procs = num_cpus::get() as isize;
                 let configs = whitebox_common::configs::get_configs()?;
-                let max_procs = configs.max_procs;
-                if max_procs > 0 && max_procs < num_procs {
-                    num_procs = max_procs;
-                }
+                let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
                 let (tx, rx) = mpsc::channel();
                 for tid in 0..num_procs {
                     let input = input.clone();
                     let tx1 = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         let nodata = input.configs.nodata;
                         let columns = input.configs.columns as isize;
                         let d_x = [1, 1, 1, 0, -1, -1, -1, 0];
@@ -466,10 +418,10 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                             let mut data = vec![nodata; columns as usize];
                             for col in 0..columns {
                                 z = input[(row, col)];
-                                if z != nodata {
+                                if !is_nodata(z, nodata) {
                                     for c in 0..8 {
                                         n[c] = input[(row + d_y[c], col + d_x[c])];
-                                        if n[c] != nodata {
+                                        if !is_nodata(n[c], nodata) {
                                             n[c] = n[c] * z_factor;
                                         } else {
                                             n[c] = z * z_factor;
@@ -484,7 +436,9 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                                         (fx * fx + fy * fy).sqrt().atan().to_degrees();
                                 }
                             }
-                            tx1.send((row, data)).unwrap();
+                            if tx1.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -494,26 +448,24 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                     let data = rx.recv().expect("Error receiving data from thread.");
                     slope.set_row_data(data.0, data.1);
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Slope analysis: {}%", progress);
+                            reporter.progress("Slope analysis", progress);
                             old_progress = progress;
                         }
                     }
                 }
 
                 let mut watershed_file = watershed_files[i].to_string();
-                if !watershed_file.contains(&sep) {
-                    watershed_file = format!("{}{}", working_directory, watershed_file);
-                }
+                watershed_file = resolve_input_path(&watershed_file, working_directory)?;
                 let watershed = Raster::new(&watershed_file, "r")?;
                 let ws_nodata = watershed.configs.nodata;
                 if watershed.configs.rows as isize != rows
                     || watershed.configs.columns as isize != columns
                 {
-                    return Err(Error::new(ErrorKind::InvalidInput,
-                            "The input DEM and watershed rasters should have the same extents (rows and columns)."));
+                    return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input DEM and watershed rasters should have the same extents (rows and columns)."));
                 }
 
                 let watershed_min = watershed.configs.minimum;
@@ -530,9 +482,9 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             val = watershed.get_value(row, col);
-                            if val != 0f64 && val != ws_nodata {
+                            if val != 0f64 && !is_nodata(val, ws_nodata) {
                                 watershed_id = (val - watershed_min) as usize;
                                 if z < min_elevs[watershed_id] {
                                     min_elevs[watershed_id] = z;
@@ -543,10 +495,11 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Processing watershed data: {}%", progress);
+                            reporter.progress("Processing watershed data", progress);
                             old_progress = progress;
                         }
                     }
@@ -585,9 +538,9 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                 for row in 0..rows {
                     for col in 0..columns {
                         z = input.get_value(row, col);
-                        if z != nodata {
+                        if !is_nodata(z, nodata) {
                             val = watershed.get_value(row, col);
-                            if val != 0f64 && val != ws_nodata {
+                            if val != 0f64 && !is_nodata(val, ws_nodata) {
                                 watershed_id = (val - watershed_min) as usize;
                                 bin = ((z - min_elevs[watershed_id]) / bin_widths[watershed_id])
                                     .floor() as usize;
@@ -596,10 +549,11 @@ impl WhiteboxTool for SlopeVsElevationPlot {
                             }
                         }
                     }
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Binning the data: {}%", progress);
+                            reporter.progress("Binning the data", progress);
                             old_progress = progress;
                         }
                     }
@@ -0,0 +1,373 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+// The grid parameters and surface generators shared by the synthetic terrain tools, i.e.
+// FractalTerrain, GaussianHills, PerlinNoiseTerrain, and SyntheticValley, and by the self tests.
+//
+// Random values are drawn from a hash of the seed and the position (e.g. the row and column of a
+// cell) at which they are used, rather than from a sequence, so that a surface depends only on
+// its parameters and seed. It is the same across platforms, numbers of threads, and releases of
+// the rand crate, which the surfaces need as fixtures for regression tests.
+
+use crate::tools::*;
+use std::f64::consts::PI;
+use std::io::{Error, ErrorKind};
+use std::sync::mpsc;
+use std::sync::Arc;
+use whitebox_common::utils::spawn;
+use whitebox_raster::*;
+
+/// The ratio of the side slope to the down-valley slope of a synthetic valley above which each
+/// flank cell drains directly towards the valley axis under D8 (1 + sqrt(2)).
+pub(super) const VALLEY_SLOPE_RATIO: f64 = 2.414213562373095;
+
+/// Returns the parameters that describe the grid of a synthetic terrain, i.e. a base raster or
+/// the numbers of rows and columns and the cell size.
+pub(super) fn grid_parameters() -> Vec<ToolParameter> {
+    vec![
+        ToolParameter {
+            name: "Input Base File".to_owned(),
+            flags: vec!["--base".to_owned()],
+            description: "Optional base raster, whose grid and coordinate reference system the output takes.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        },
+        ToolParameter {
+            name: "Number of Rows".to_owned(),
+            flags: vec!["--rows".to_owned()],
+            description: "Number of rows, if no base raster is given.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("512".to_owned()),
+            optional: true,
+        },
+        ToolParameter {
+            name: "Number of Columns".to_owned(),
+            flags: vec!["--columns".to_owned()],
+            description: "Number of columns, if no base raster is given.".to_owned(),
+            parameter_type: ParameterType::Integer,
+            default_value: Some("512".to_owned()),
+            optional: true,
+        },
+        ToolParameter {
+            name: "Cell Size".to_owned(),
+            flags: vec!["--cell_size".to_owned()],
+            description: "Grid cell size, if no base raster is given.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("10.0".to_owned()),
+            optional: true,
+        },
+    ]
+}
+
+/// Returns the seed parameter of the random synthetic terrains.
+pub(super) fn seed_parameter() -> ToolParameter {
+    ToolParameter {
+        name: "Random Seed".to_owned(),
+        flags: vec!["--seed".to_owned()],
+        description: "Seed of the random values; the same seed and parameters give the same surface.".to_owned(),
+        parameter_type: ParameterType::Integer,
+        default_value: Some("42".to_owned()),
+        optional: true,
+    }
+}
+
+/// Returns the configs of the output grid, taken from the base raster (`--base`) if one is given
+/// and otherwise from `--rows`, `--columns`, and `--cell_size`, with the grid's lower-left corner
+/// at the origin.
+pub(super) fn output_configs(parsed: &ParsedArgs, working_directory: &str) -> Result<RasterConfigs, Error> {
+    let mut configs = match parsed.get_string("base") {
+        Some(base) if !base.trim().is_empty() => {
+            let base = Raster::new(&resolve_input_path(base.trim(), working_directory)?, "r")?;
+            base.configs.clone()
+        }
+        _ => {
+            let rows = parsed.get_usize("rows")?.unwrap_or(512);
+            let columns = parsed.get_usize("columns")?.unwrap_or(512);
+            let cell_size = parsed.get_f64("cell_size").unwrap_or(10.0);
+            if rows < 2 || columns < 2 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The output must have at least two rows and two columns.",
+                ));
+            }
+            if cell_size <= 0.0 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The --cell_size parameter must be greater than zero.",
+                ));
+            }
+            RasterConfigs {
+                rows: rows,
+                columns: columns,
+                north: rows as f64 * cell_size,
+                south: 0.0,
+                east: columns as f64 * cell_size,
+                west: 0.0,
+                resolution_x: cell_size,
+                resolution_y: cell_size,
+                ..Default::default()
+            }
+        }
+    };
+    configs.nodata = -32768.0;
+    configs.data_type = DataType::F32;
+    configs.photometric_interp = PhotometricInterpretation::Continuous;
+    configs.palette = "relief.plt".to_string();
+    configs.z_units = "not specified".to_string();
+    Ok(configs)
+}
+
+/// Returns a random value in [-1, 1) that depends only on the seed and the integers `a` and `b`,
+/// e.g. the row and column of a cell. The SplitMix64 finalizer is applied to each input in turn.
+pub(super) fn random(seed: u64, a: i64, b: i64) -> f64 {
+    fn mix(z: u64) -> u64 {
+        let mut z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    let z = mix(mix(mix(seed).wrapping_add(a as u64)).wrapping_add(b as u64));
+    (z >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+}
+
+/// Computes each cell of a surface with `f(row, col)`, in parallel, and returns the surface as rows.
+pub(super) fn compute_surface<F>(
+    rows: isize,
+    columns: isize,
+    f: F,
+    verbose: bool,
+    reporter: &dyn ProgressReporter,
+) -> Result<Vec<Vec<f64>>, Error>
+where
+    F: Fn(isize, isize) -> f64 + Send + Sync + 'static,
+{
+    let f = Arc::new(f);
+    let num_procs = whitebox_common::utils::ThreadPool::from_settings().max_threads(rows as usize).num_threads() as isize;
+    let (tx, rx) = mpsc::channel();
+    for tid in 0..num_procs {
+        let f = f.clone();
+        let tx = tx.clone();
+        spawn(move || {
+            for row in (0..rows).filter(|r| r % num_procs == tid) {
+                let data: Vec<f64> = (0..columns).map(|col| f(row, col)).collect();
+                if tx.send((row, data)).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+    let mut surface = vec![vec![]; rows as usize];
+    let mut old_progress = usize::MAX;
+    for r in 0..rows {
+        let (row, data) = rx.recv().expect("Error receiving data from thread.");
+        surface[row as usize] = data;
+        reporter.check_cancelled()?;
+        if verbose {
+            let progress = (100.0_f64 * r as f64 / (rows - 1).max(1) as f64) as usize;
+            if progress != old_progress {
+                reporter.progress("Generating surface", progress);
+                old_progress = progress;
+            }
+        }
+    }
+    Ok(surface)
+}
+
+/// Scales a surface to the range [0, `relief`].
+pub(super) fn rescale(surface: &mut [Vec<f64>], relief: f64) {
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    for z in surface.iter().flatten() {
+        min = min.min(*z);
+        max = max.max(*z);
+    }
+    let range = max - min;
+    for z in surface.iter_mut().flatten() {
+        *z = if range > 0.0 { (*z - min) / range * relief } else { 0.0 };
+    }
+}
+
+/// Writes a surface, given as rows, to `output_file` on the grid of `configs`.
+pub(super) fn write_surface(
+    output_file: &str,
+    configs: &RasterConfigs,
+    surface: Vec<Vec<f64>>,
+    metadata: Vec<String>,
+    verbose: bool,
+) -> Result<(), Error> {
+    let mut output = Raster::initialize_using_config(output_file, configs);
+    for (row, data) in surface.into_iter().enumerate() {
+        output.set_row_data(row as isize, data);
+    }
+    for entry in metadata {
+        output.add_metadata_entry(entry);
+    }
+    if verbose {
+        println!("Saving data...")
+    };
+    let _ = match output.write() {
+        Ok(_) => {
+            if verbose {
+                println!("Output file written")
+            }
+        }
+        Err(e) => return Err(e),
+    };
+    Ok(())
+}
+
+/// Generates a fractal surface by the diamond-square algorithm. The surface is generated on the
+/// smallest square grid of 2^n + 1 cells that covers the output and is then cropped. `roughness`
+/// is the Hurst exponent (0 < H <= 1): the random displacements are scaled by 2^-H at each level
+/// of subdivision, such that lower values give rougher surfaces. The surface is unscaled.
+pub(super) fn diamond_square(rows: usize, columns: usize, roughness: f64, seed: u64) -> Vec<Vec<f64>> {
+    let mut size = 1usize;
+    while size + 1 < rows.max(columns) {
+        size *= 2;
+    }
+    let n = size + 1;
+    let mut z = vec![vec![0f64; n]; n];
+    for (r, c) in [(0, 0), (0, size), (size, 0), (size, size)] {
+        z[r][c] = random(seed, r as i64, c as i64);
+    }
+    let factor = 2f64.powf(-roughness);
+    let mut scale = factor;
+    let mut step = size;
+    while step > 1 {
+        let half = step / 2;
+        // diamond step: the centre of each square
+        for r in (half..n).step_by(step) {
+            for c in (half..n).step_by(step) {
+                let mean = (z[r - half][c - half] + z[r - half][c + half] + z[r + half][c - half] + z[r + half][c + half]) / 4.0;
+                z[r][c] = mean + scale * random(seed, r as i64, c as i64);
+            }
+        }
+        // square step: the midpoint of each edge, from its (up to four) neighbours
+        for r in (0..n).step_by(half) {
+            let first = if (r / half) % 2 == 0 { half } else { 0 };
+            for c in (first..n).step_by(step) {
+                let mut sum = 0.0;
+                let mut count = 0.0;
+                if r >= half {
+                    sum += z[r - half][c];
+                    count += 1.0;
+                }
+                if r + half < n {
+                    sum += z[r + half][c];
+                    count += 1.0;
+                }
+                if c >= half {
+                    sum += z[r][c - half];
+                    count += 1.0;
+                }
+                if c + half < n {
+                    sum += z[r][c + half];
+                    count += 1.0;
+                }
+                z[r][c] = sum / count + scale * random(seed, r as i64, c as i64);
+            }
+        }
+        scale *= factor;
+        step = half;
+    }
+    z.truncate(rows);
+    for row in z.iter_mut() {
+        row.truncate(columns);
+    }
+    z
+}
+
+/// A Gaussian hill, centred at (x, y) in map units.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct GaussianHill {
+    pub x: f64,
+    pub y: f64,
+    pub height: f64,
+    pub sigma: f64,
+}
+
+impl GaussianHill {
+    pub fn elevation(&self, x: f64, y: f64) -> f64 {
+        let d2 = (x - self.x) * (x - self.x) + (y - self.y) * (y - self.y);
+        self.height * (-d2 / (2.0 * self.sigma * self.sigma)).exp()
+    }
+}
+
+/// Returns `num_hills` Gaussian hills placed at random within the grid of `configs`, with heights
+/// between half of and the full `relief` and standard deviations (radii) between half of and
+/// one-and-a-half times `radius`.
+pub(super) fn gaussian_hills(configs: &RasterConfigs, num_hills: usize, relief: f64, radius: f64, seed: u64) -> Vec<GaussianHill> {
+    let unit = |hill: usize, k: i64| (random(seed, hill as i64, k) + 1.0) / 2.0;
+    (0..num_hills)
+        .map(|i| GaussianHill {
+            x: configs.west + unit(i, 0) * (configs.east - configs.west),
+            y: configs.south + unit(i, 1) * (configs.north - configs.south),
+            height: relief * (0.5 + 0.5 * unit(i, 2)),
+            sigma: radius * (0.5 + unit(i, 3)),
+        })
+        .collect()
+}
+
+/// Returns the value of two-dimensional Perlin (gradient) noise at (x, y), in units of the lattice
+/// spacing, which lies approximately within [-1, 1]. The gradient at each lattice point is a unit
+/// vector with a random direction.
+pub(super) fn perlin(x: f64, y: f64, seed: u64) -> f64 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (i, j) = (x0 as i64, y0 as i64);
+    let gradient = |i: i64, j: i64, dx: f64, dy: f64| {
+        let angle = PI * random(seed, i, j);
+        angle.cos() * dx + angle.sin() * dy
+    };
+    let fade = |t: f64| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let (u, v) = (fade(fx), fade(fy));
+    let n00 = gradient(i, j, fx, fy);
+    let n10 = gradient(i + 1, j, fx - 1.0, fy);
+    let n01 = gradient(i, j + 1, fx, fy - 1.0);
+    let n11 = gradient(i + 1, j + 1, fx - 1.0, fy - 1.0);
+    let nx0 = n00 + u * (n10 - n00);
+    let nx1 = n01 + u * (n11 - n01);
+    // scaled such that the extreme values of the noise approach -1 and 1
+    std::f64::consts::SQRT_2 * (nx0 + v * (nx1 - nx0))
+}
+
+/// Returns the value of fractal Perlin noise, i.e. the sum of `octaves` octaves of noise of
+/// halving wavelengths, starting at `wavelength`, and amplitudes scaled by `persistence`.
+pub(super) fn fractal_perlin(x: f64, y: f64, wavelength: f64, octaves: usize, persistence: f64, seed: u64) -> f64 {
+    let mut sum = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0 / wavelength;
+    for octave in 0..octaves {
+        sum += amplitude * perlin(x * frequency, y * frequency, seed.wrapping_add(octave as u64));
+        amplitude *= persistence;
+        frequency *= 2.0;
+    }
+    sum
+}
+
+/// The elevation of a V-shaped synthetic valley at `x` map units across the valley from its axis
+/// and `y` map units up the valley from its outlet.
+pub(crate) fn valley_elevation(x: f64, y: f64, side_slope: f64, valley_slope: f64) -> f64 {
+    side_slope * x.abs() + valley_slope * y
+}
+
+/// The D8 flow accumulation, in cells, of a cell of a synthetic valley occupying a grid of
+/// `columns` columns, whose axis is the middle column (`columns / 2`) and outlet the last row. Flank
+/// cells drain across the valley to the axis, which drains down the valley, provided that the side
+/// slope exceeds `VALLEY_SLOPE_RATIO` times the down-valley slope.
+pub(crate) fn valley_flow_accumulation(row: isize, col: isize, columns: isize) -> f64 {
+    let axis = columns / 2;
+    if col < axis {
+        (col + 1) as f64
+    } else if col > axis {
+        (columns - col) as f64
+    } else {
+        ((row + 1) * columns) as f64
+    }
+}
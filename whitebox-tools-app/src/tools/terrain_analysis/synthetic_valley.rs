@@ -0,0 +1,241 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::synthetic_terrain::*;
+use crate::tools::*;
+use std::env;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool creates an analytic, V-shaped synthetic valley (`--output`) whose drainage is known
+/// exactly. The valley's axis runs north-south along the middle column of the grid, and its outlet
+/// is the southernmost cell of the axis. The elevation of each cell is s|x| + v y, where x is the
+/// distance across the valley from its axis, y the distance up the valley from its outlet, s the
+/// side slope (`--side_slope`), and v the down-valley slope (`--valley_slope`). The surface has no
+/// pits or flats, and when the side slope exceeds 1 + sqrt(2) (about 2.414) times the valley slope,
+/// every flank cell drains across the valley to the axis under D8 flow routing, and the axis drains
+/// to the outlet. The known D8 flow accumulation, in cells, can then be written to a second output
+/// (`--accum`); it is the number of cells between a flank cell and the edge of the grid, inclusive,
+/// and, for an axis cell, the number of cells in its row and the rows upstream of it. Comparing the
+/// outputs of flow routing tools against it is a simple test of their correctness.
+///
+/// The output grid is that of a base raster (`--base`), if one is given, and otherwise has the
+/// given numbers of rows (`--rows`) and columns (`--columns`) and cell size (`--cell_size`), with
+/// its lower-left corner at the origin. The surface involves no random values.
+///
+/// # See Also
+/// `FractalTerrain`, `GaussianHills`, `PerlinNoiseTerrain`, `D8FlowAccumulation`
+pub struct SyntheticValley {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl SyntheticValley {
+    pub fn new() -> SyntheticValley {
+        // public constructor
+        let name = "SyntheticValley".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description =
+            "Creates an analytic V-shaped valley with known drainage.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Output File".to_owned(),
+            flags: vec!["-o".to_owned(), "--output".to_owned()],
+            description: "Output raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Flow Accumulation File".to_owned(),
+            flags: vec!["--accum".to_owned()],
+            description: "Optional output raster of the known D8 flow accumulation, in cells.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.extend(grid_parameters());
+
+        parameters.push(ToolParameter {
+            name: "Side Slope".to_owned(),
+            flags: vec!["--side_slope".to_owned()],
+            description: "Gradient of the valley sides, towards the axis.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.1".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Valley Slope".to_owned(),
+            flags: vec!["--valley_slope".to_owned()],
+            description: "Gradient down the valley, towards the outlet.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.01".to_owned()),
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{0} -r={1} -v --wd=\"*path*to*data*\" -o=valley.tif --accum=valley_accum.tif --rows=200 --columns=101 --cell_size=1.0 --side_slope=0.2 --valley_slope=0.02", short_exe, name).replace("*", &sep);
+
+        SyntheticValley {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for SyntheticValley {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        let mut s = String::from("{\"parameters\": [");
+        for i in 0..self.parameters.len() {
+            if i < self.parameters.len() - 1 {
+                s.push_str(&(self.parameters[i].to_string()));
+                s.push_str(",");
+            } else {
+                s.push_str(&(self.parameters[i].to_string()));
+            }
+        }
+        s.push_str("]}");
+        s
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let output_file = resolve_path(&parsed.get_string("output").unwrap_or_default(), working_directory);
+        let accum_file = parsed
+            .get_string("accum")
+            .filter(|f| !f.trim().is_empty())
+            .map(|f| resolve_path(f.trim(), working_directory));
+        let side_slope = parsed.get_f64("side_slope").unwrap_or(0.1);
+        let valley_slope = parsed.get_f64("valley_slope").unwrap_or(0.01);
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        if side_slope <= 0.0 || valley_slope <= 0.0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The --side_slope and --valley_slope parameters must be greater than zero.",
+            ));
+        }
+        if accum_file.is_some() && side_slope <= VALLEY_SLOPE_RATIO * valley_slope {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "The drainage of the valley is only known when the --side_slope exceeds {:.3} times the --valley_slope.",
+                    VALLEY_SLOPE_RATIO
+                ),
+            ));
+        }
+
+        let configs = output_configs(&parsed, working_directory)?;
+
+        let start = Instant::now();
+
+        let rows = configs.rows as isize;
+        let columns = configs.columns as isize;
+        let (res_x, res_y) = (configs.resolution_x, configs.resolution_y);
+        let surface = compute_surface(
+            rows,
+            columns,
+            move |row, col| {
+                let x = (col - columns / 2) as f64 * res_x;
+                let y = (rows - 1 - row) as f64 * res_y;
+                valley_elevation(x, y, side_slope, valley_slope)
+            },
+            verbose,
+            reporter,
+        )?;
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        let metadata = vec![
+            format!("Created by whitebox_tools\' {} tool", self.get_tool_name()),
+            format!("Side slope: {}", side_slope),
+            format!("Valley slope: {}", valley_slope),
+            format!("Elapsed Time (excluding I/O): {}", elapsed_time),
+        ];
+        write_surface(&output_file, &configs, surface, metadata, verbose)?;
+
+        if let Some(accum_file) = accum_file {
+            let accum: Vec<Vec<f64>> = (0..rows)
+                .map(|row| (0..columns).map(|col| valley_flow_accumulation(row, col, columns)).collect())
+                .collect();
+            let mut accum_configs = configs.clone();
+            accum_configs.palette = "blueyellow.plt".to_string();
+            let metadata = vec![
+                format!("Created by whitebox_tools\' {} tool", self.get_tool_name()),
+                "Known D8 flow accumulation (cells)".to_string(),
+            ];
+            write_surface(&accum_file, &accum_configs, accum, metadata, verbose)?;
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
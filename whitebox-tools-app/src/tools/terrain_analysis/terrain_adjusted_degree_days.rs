@@ -0,0 +1,505 @@
+/*
+This tool is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+*/
+
+use super::raster_or_constant::RasterOrConstant;
+use whitebox_raster::*;
+use crate::tools::*;
+use std::env;
+use std::f64;
+use std::io::{Error, ErrorKind};
+use std::path;
+
+/// This tool prepares terrain-adjusted seasonal thawing and freezing degree-day surfaces for use in
+/// temperature-at-the-top-of-permafrost (TTOP) type permafrost models (Smith and Riseborough, 1996). Air
+/// thawing (`--tdd`) and freezing (`--fdd`) degree-days (°C·days, with freezing degree-days expressed as positive
+/// values) are first adjusted for elevation and topographic solar exposure and then converted to ground-surface
+/// thawing and freezing indices using thawing (`--nt`) and freezing (`--nf`) n-factors.
+///
+/// The degree-day, n-factor, and thermal conductivity ratio inputs may each be specified either as a constant
+/// value or as a raster with the same dimensions as the DEM (`--dem`). If a reference elevation (`--ref_elev`) is
+/// specified, the air degree-days are treated as measurements at that elevation and are extrapolated to the
+/// elevation of each grid cell using a temperature lapse rate (`--lapse_rate`, °C/km) applied over the lengths of
+/// the thawing (`--thaw_days`) and freezing (`--freeze_days`) seasons:
+///
+/// > TDD<sub>z</sub> = max(TDD + D<sub>t</sub> Γ (z<sub>ref</sub> - z), 0)
+///
+/// > FDD<sub>z</sub> = max(FDD - D<sub>f</sub> Γ (z<sub>ref</sub> - z), 0)
+///
+/// where *D<sub>t</sub>* and *D<sub>f</sub>* are the season lengths and *Γ* is the lapse rate.
+///
+/// If a raster of potential incoming solar radiation for the thawing season (`--radiation`) is specified, thawing
+/// degree-days are further scaled by the relative radiation of each cell, such that equator-facing slopes
+/// receive more thawing and pole-facing slopes less:
+///
+/// > TDD<sub>adj</sub> = TDD<sub>z</sub> max(1 + k (R / R<sub>ref</sub> - 1), 0)
+///
+/// where *k* is the radiation coefficient (`--rad_coef`) and *R<sub>ref</sub>* is the reference radiation
+/// (`--rad_ref`), which defaults to the mean of the radiation raster. The radiation raster may be in any units,
+/// provided that *R<sub>ref</sub>* is in the same units. The ground-surface thawing index (`--out_tdd`) is then
+/// *n<sub>t</sub> TDD<sub>adj</sub>* and the ground-surface freezing index (`--out_fdd`) is *n<sub>f</sub>
+/// FDD<sub>z</sub>*.
+///
+/// Optionally, the TTOP (°C) may be output (`--out_ttop`), given the ratio of thawed to frozen ground thermal
+/// conductivity (`--rk`):
+///
+/// > TTOP = (r<sub>k</sub> n<sub>t</sub> TDD - n<sub>f</sub> FDD) / P, where r<sub>k</sub> n<sub>t</sub> TDD ≤ n<sub>f</sub> FDD
+///
+/// > TTOP = (n<sub>t</sub> TDD - n<sub>f</sub> FDD / r<sub>k</sub>) / P, otherwise
+///
+/// where *P* is the period (365 days). Negative TTOP values indicate conditions favourable to permafrost. At
+/// least one output must be specified.
+///
+/// # Reference
+/// Smith, M. W., and Riseborough, D. W. (1996). Permafrost monitoring and detection of climate change.
+/// *Permafrost and Periglacial Processes*, 7(4), 301-309.
+///
+/// # See Also
+/// `TimeInDaylight`, `HorizonAngle`
+pub struct TerrainAdjustedDegreeDays {
+    name: String,
+    description: String,
+    toolbox: String,
+    parameters: Vec<ToolParameter>,
+    example_usage: String,
+}
+
+impl TerrainAdjustedDegreeDays {
+    pub fn new() -> TerrainAdjustedDegreeDays {
+        // public constructor
+        let name = "TerrainAdjustedDegreeDays".to_string();
+        let toolbox = "Geomorphometric Analysis".to_string();
+        let description = "Calculates terrain-adjusted ground-surface thawing and freezing indices and TTOP for permafrost modelling.".to_string();
+
+        let mut parameters = vec![];
+        parameters.push(ToolParameter {
+            name: "Input DEM File".to_owned(),
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
+            description: "Input raster DEM file.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Air Thawing Degree-Days".to_owned(),
+            flags: vec!["--tdd".to_owned()],
+            description: "Air thawing degree-days; a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Air Freezing Degree-Days".to_owned(),
+            flags: vec!["--fdd".to_owned()],
+            description: "Air freezing degree-days, as positive values; a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: None,
+            optional: false,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Thawing n-Factor".to_owned(),
+            flags: vec!["--nt".to_owned()],
+            description: "Thawing n-factor; a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Freezing n-Factor".to_owned(),
+            flags: vec!["--nf".to_owned()],
+            description: "Freezing n-factor; a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reference Elevation (optional)".to_owned(),
+            flags: vec!["--ref_elev".to_owned()],
+            description: "Elevation of the air degree-day measurements; if unspecified, no lapse rate adjustment is applied.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Lapse Rate (degrees C per km)".to_owned(),
+            flags: vec!["--lapse_rate".to_owned()],
+            description: "Air temperature lapse rate, in degrees C per km.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("6.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Thawing Season Length (days)".to_owned(),
+            flags: vec!["--thaw_days".to_owned()],
+            description: "Length of the thawing season, in days.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("120.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Freezing Season Length (days)".to_owned(),
+            flags: vec!["--freeze_days".to_owned()],
+            description: "Length of the freezing season, in days.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("245.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Input Thawing Season Radiation File (optional)".to_owned(),
+            flags: vec!["--radiation".to_owned()],
+            description: "Input raster of potential incoming solar radiation during the thawing season.".to_owned(),
+            parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Radiation Coefficient".to_owned(),
+            flags: vec!["--rad_coef".to_owned()],
+            description: "Sensitivity of thawing degree-days to relative radiation.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: Some("0.5".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Reference Radiation (optional)".to_owned(),
+            flags: vec!["--rad_ref".to_owned()],
+            description: "Reference radiation value; defaults to the mean of the radiation raster.".to_owned(),
+            parameter_type: ParameterType::Float,
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Thermal Conductivity Ratio".to_owned(),
+            flags: vec!["--rk".to_owned()],
+            description: "Ratio of thawed to frozen ground thermal conductivity; a raster file or constant value.".to_owned(),
+            parameter_type: ParameterType::ExistingFileOrFloat(ParameterFileType::Raster),
+            default_value: Some("1.0".to_owned()),
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Thawing Index File (optional)".to_owned(),
+            flags: vec!["--out_tdd".to_owned()],
+            description: "Output ground-surface thawing index raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output Freezing Index File (optional)".to_owned(),
+            flags: vec!["--out_fdd".to_owned()],
+            description: "Output ground-surface freezing index raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        parameters.push(ToolParameter {
+            name: "Output TTOP File (optional)".to_owned(),
+            flags: vec!["--out_ttop".to_owned()],
+            description: "Output temperature at the top of permafrost (TTOP) raster file.".to_owned(),
+            parameter_type: ParameterType::NewFile(ParameterFileType::Raster),
+            default_value: None,
+            optional: true,
+        });
+
+        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let e = format!("{}", env::current_exe().unwrap().display());
+        let mut parent = env::current_exe().unwrap();
+        parent.pop();
+        let p = format!("{}", parent.display());
+        let mut short_exe = e
+            .replace(&p, "")
+            .replace(".exe", "")
+            .replace(".", "")
+            .replace(&sep, "");
+        if e.contains(".exe") {
+            short_exe += ".exe";
+        }
+        let usage = format!(">>.*{} -r={} -v --wd=\"*path*to*data*\" --dem=DEM.tif --tdd=850.0 --fdd=2900.0 --ref_elev=450.0 --radiation=summer_rad.tif --nt=nt.tif --nf=0.4 --out_tdd=thaw_index.tif --out_fdd=freeze_index.tif --out_ttop=ttop.tif --rk=0.7", short_exe, name).replace("*", &sep);
+
+        TerrainAdjustedDegreeDays {
+            name: name,
+            description: description,
+            toolbox: toolbox,
+            parameters: parameters,
+            example_usage: usage,
+        }
+    }
+}
+
+impl WhiteboxTool for TerrainAdjustedDegreeDays {
+    fn get_source_file(&self) -> String {
+        String::from(file!())
+    }
+
+    fn get_tool_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_tool_description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn get_tool_parameters(&self) -> String {
+        match serde_json::to_string(&self.parameters) {
+            Ok(json_str) => return format!("{{\"parameters\":{}}}", json_str),
+            Err(err) => return format!("{:?}", err),
+        }
+    }
+
+    fn get_example_usage(&self) -> String {
+        self.example_usage.clone()
+    }
+
+    fn get_toolbox(&self) -> String {
+        self.toolbox.clone()
+    }
+
+    fn run<'a>(
+        &self,
+        args: Vec<String>,
+        working_directory: &'a str,
+        verbose: bool,
+        reporter: &dyn ProgressReporter,
+    ) -> Result<(), Error> {
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut dem_file = parsed.get_string("dem").unwrap_or_default();
+        let tdd = parsed.get_string("tdd").unwrap_or_default();
+        let fdd = parsed.get_string("fdd").unwrap_or_default();
+        let nt = parsed.get_string("nt").unwrap_or("1.0".to_string());
+        let nf = parsed.get_string("nf").unwrap_or("1.0".to_string());
+        let rk = parsed.get_string("rk").unwrap_or("1.0".to_string());
+        let ref_elev = parsed.get_f64("ref_elev").unwrap_or(f64::NAN);
+        let lapse_rate = parsed.get_f64("lapse_rate").unwrap_or(6.5f64);
+        let thaw_days = parsed.get_f64("thaw_days").unwrap_or(120f64);
+        let freeze_days = parsed.get_f64("freeze_days").unwrap_or(245f64);
+        let mut radiation_file = parsed.get_string("radiation").unwrap_or_default();
+        let rad_coef = parsed.get_f64("rad_coef").unwrap_or(0.5f64);
+        let mut rad_ref = parsed.get_f64("rad_ref").unwrap_or(f64::NAN);
+        let mut tdd_file = parsed.get_string("out_tdd").unwrap_or_default();
+        let mut fdd_file = parsed.get_string("out_fdd").unwrap_or_default();
+        let mut ttop_file = parsed.get_string("out_ttop").unwrap_or_default();
+
+        if verbose {
+            let tool_name = self.get_tool_name();
+            let welcome_len = format!("* Welcome to {} *", tool_name).len().max(28);
+            // 28 = length of the 'Powered by' by statement.
+            println!("{}", "*".repeat(welcome_len));
+            println!("* Welcome to {} {}*", tool_name, " ".repeat(welcome_len - 15 - tool_name.len()));
+            println!("* Powered by WhiteboxTools {}*", " ".repeat(welcome_len - 28));
+            println!("* www.whiteboxgeo.com {}*", " ".repeat(welcome_len - 23));
+            println!("{}", "*".repeat(welcome_len));
+        }
+
+        let mut progress: usize;
+        let mut old_progress: usize = 1;
+
+        if tdd_file.is_empty() && fdd_file.is_empty() && ttop_file.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one of the thawing index (--out_tdd), freezing index (--out_fdd), and TTOP (--out_ttop) outputs must be specified.",
+            ));
+        }
+        if tdd.is_empty() || fdd.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "The air thawing (--tdd) and freezing (--fdd) degree-days must be specified.",
+            ));
+        }
+        for file in [&mut dem_file, &mut radiation_file, &mut tdd_file, &mut fdd_file, &mut ttop_file] {
+            *file = resolve_path(file, working_directory);
+        }
+
+        if verbose {
+            println!("Reading data...")
+        };
+        let dem = Raster::new(&dem_file, "r")?;
+        let rows = dem.configs.rows as isize;
+        let columns = dem.configs.columns as isize;
+        let nodata = dem.configs.nodata;
+        let tdd = RasterOrConstant::new(&tdd, working_directory, &dem)?;
+        let fdd = RasterOrConstant::new(&fdd, working_directory, &dem)?;
+        let nt = RasterOrConstant::new(&nt, working_directory, &dem)?;
+        let nf = RasterOrConstant::new(&nf, working_directory, &dem)?;
+        let rk = RasterOrConstant::new(&rk, working_directory, &dem)?;
+        let radiation = if !radiation_file.is_empty() {
+            let r = Raster::new(&radiation_file, "r")?;
+            if r.configs.rows != dem.configs.rows || r.configs.columns != dem.configs.columns {
+                return Err(whitebox_common::errors::ErrorCause::GridMismatch.error("The input files must have the same number of rows and columns and spatial extent."));
+            }
+            warn_if_crs_differs(&r, &dem, verbose);
+            Some(r)
+        } else {
+            None
+        };
+
+        let start = Instant::now();
+
+        if let Some(ref r) = radiation {
+            if rad_ref.is_nan() {
+                let (mut sum, mut n) = (0f64, 0f64);
+                for row in 0..rows {
+                    reporter.check_cancelled()?;
+                    for col in 0..columns {
+                        let v = r.get_value(row, col);
+                        if !is_nodata(v, r.configs.nodata) {
+                            sum += v;
+                            n += 1f64;
+                        }
+                    }
+                }
+                rad_ref = if n > 0f64 { sum / n } else { 0f64 };
+            }
+            if rad_ref <= 0f64 {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "The reference radiation (--rad_ref) must be greater than zero.",
+                ));
+            }
+        }
+
+        let out_nodata = -32768f64;
+        let initialize_output = |file: &str, palette: &str| {
+            if file.is_empty() {
+                return None;
+            }
+            let mut output = Raster::initialize_using_file(file, &dem);
+            output.configs.nodata = out_nodata;
+            output.configs.data_type = DataType::F32;
+            output.configs.photometric_interp = PhotometricInterpretation::Continuous;
+            output.configs.palette = palette.to_string();
+            output.reinitialize_values(out_nodata);
+            Some(output)
+        };
+        let mut tdd_output = initialize_output(&tdd_file, "spectrum.plt");
+        let mut fdd_output = initialize_output(&fdd_file, "spectrum.plt");
+        let mut ttop_output = initialize_output(&ttop_file, "blue_white_red.plt");
+
+        const PERIOD: f64 = 365f64;
+        let lapse = lapse_rate / 1000f64; // °C/m
+        let mut z: f64;
+        let mut values = [0f64; 5];
+        for row in 0..rows {
+            'col: for col in 0..columns {
+                z = dem.get_value(row, col);
+                if is_nodata(z, nodata) {
+                    continue;
+                }
+                for (i, param) in [&tdd, &fdd, &nt, &nf, &rk].iter().enumerate() {
+                    values[i] = match param.get_value(row, col) {
+                        Some(v) => v,
+                        None => continue 'col,
+                    };
+                }
+                let (mut thaw, mut freeze) = (values[0], values[1]);
+                let (n_t, n_f, r_k) = (values[2], values[3], values[4]);
+                if !ref_elev.is_nan() {
+                    let dt = lapse * (ref_elev - z);
+                    thaw = (thaw + thaw_days * dt).max(0f64);
+                    freeze = (freeze - freeze_days * dt).max(0f64);
+                }
+                if let Some(ref rad) = radiation {
+                    let r = rad.get_value(row, col);
+                    if is_nodata(r, rad.configs.nodata) {
+                        continue;
+                    }
+                    thaw *= (1f64 + rad_coef * (r / rad_ref - 1f64)).max(0f64);
+                }
+                let thaw_index = n_t * thaw;
+                let freeze_index = n_f * freeze;
+                if let Some(ref mut output) = tdd_output {
+                    output.set_value(row, col, thaw_index);
+                }
+                if let Some(ref mut output) = fdd_output {
+                    output.set_value(row, col, freeze_index);
+                }
+                if let Some(ref mut output) = ttop_output {
+                    if r_k > 0f64 {
+                        let ttop = if r_k * thaw_index <= freeze_index {
+                            (r_k * thaw_index - freeze_index) / PERIOD
+                        } else {
+                            (thaw_index - freeze_index / r_k) / PERIOD
+                        };
+                        output.set_value(row, col, ttop);
+                    }
+                }
+            }
+            reporter.check_cancelled()?;
+            if verbose {
+                progress = (100.0_f64 * row as f64 / (rows - 1).max(1) as f64) as usize;
+                if progress != old_progress {
+                    reporter.progress("Progress", progress);
+                    old_progress = progress;
+                }
+            }
+        }
+
+        let elapsed_time = get_formatted_elapsed_time(start);
+        for (output, label, units) in [
+            (tdd_output, "Ground-surface thawing index", "degree-days"),
+            (fdd_output, "Ground-surface freezing index", "degree-days"),
+            (ttop_output, "TTOP", "degrees C"),
+        ] {
+            let mut output = match output {
+                Some(o) => o,
+                None => continue,
+            };
+            output.configs.z_units = units.to_string();
+            output.add_metadata_entry(format!(
+                "Created by whitebox_tools\' {} tool",
+                self.get_tool_name()
+            ));
+            output.add_metadata_entry(format!("{}", label));
+            output.add_metadata_entry(format!("Input DEM file: {}", dem_file));
+            if !ref_elev.is_nan() {
+                output.add_metadata_entry(format!("Reference elevation: {}", ref_elev));
+                output.add_metadata_entry(format!("Lapse rate: {}", lapse_rate));
+            }
+            if !radiation_file.is_empty() {
+                output.add_metadata_entry(format!("Input radiation file: {}", radiation_file));
+                output.add_metadata_entry(format!("Radiation coefficient: {}", rad_coef));
+                output.add_metadata_entry(format!("Reference radiation: {}", rad_ref));
+            }
+            output.add_metadata_entry(format!("Elapsed Time (excluding I/O): {}", elapsed_time));
+
+            if verbose {
+                println!("Saving data...")
+            };
+            let _ = match output.write() {
+                Ok(_) => {
+                    if verbose {
+                        println!("Output file written")
+                    }
+                }
+                Err(e) => return Err(e),
+            };
+        }
+
+        if verbose {
+            println!(
+                "{}",
+                &format!("Elapsed Time (excluding I/O): {}", elapsed_time)
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -6,21 +6,20 @@ Last Modified: 03/09/2020
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use chrono::prelude::*;
 use chrono::{Date, FixedOffset, NaiveTime, TimeZone};
-use num_cpus;
 use rayon::prelude::*;
 use std::env;
 use std::f64;
 use std::f64::consts::PI;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool calculates the proportion of time a location is within daylight. That is, it calculates the
 /// proportion of time, during a user-defined time frame, that a grid cell in an input digital elevation
@@ -77,7 +76,7 @@ impl TimeInDaylight {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["-i".to_owned(), "--dem".to_owned()],
+            flags: vec!["--input".to_owned(), "-i".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -108,13 +107,13 @@ impl TimeInDaylight {
             description: "Optional maximum search distance. Minimum value is 5 x cell size."
                 .to_owned(),
             parameter_type: ParameterType::Float,
-            default_value: Some("100.0".to_owned()),
-            optional: false,
+            default_value: None,
+            optional: true,
         });
 
         parameters.push(ToolParameter {
             name: "Centre Point Latitude".to_owned(),
-            flags: vec!["--lat".to_owned()],
+            flags: vec!["--latitude".to_owned(), "--lat".to_owned()],
             description: "Centre point latitude.".to_owned(),
             parameter_type: ParameterType::Float,
             default_value: None,
@@ -123,7 +122,7 @@ impl TimeInDaylight {
 
         parameters.push(ToolParameter {
             name: "Centre Point Longitude".to_owned(),
-            flags: vec!["--long".to_owned()],
+            flags: vec!["--longitude".to_owned(), "--long".to_owned()],
             description: "Centre point longitude.".to_owned(),
             parameter_type: ParameterType::Float,
             default_value: None,
@@ -132,7 +131,7 @@ impl TimeInDaylight {
 
         parameters.push(ToolParameter {
             name: "UTC Offset (e.g. -04:00, +06:00)".to_owned(),
-            flags: vec!["--utc_offset".to_owned()],
+            flags: vec!["--utc".to_owned(), "--utc_offset".to_owned()],
             description: "UTC time offset, in hours (e.g. -04:00, +06:00).".to_owned(),
             parameter_type: ParameterType::String,
             default_value: Some("00:00".to_string()),
@@ -240,217 +239,110 @@ impl WhiteboxTool for TimeInDaylight {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut az_fraction = 10.0f32;
-        let mut max_dist = f32::INFINITY;
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let az_fraction = parsed.get_f64("az_fraction").map(|v| v as f32).unwrap_or(10.0f32);
+        let mut max_dist = parsed.get_f64("max_dist").map(|v| v as f32).unwrap_or(f32::INFINITY);
         let mut latitude = 0f32;
         let mut longitude = 0f32;
         // Guelph:
         // let mut latitude = 43.5448;
         // let mut longitude = -80.2482;
         let mut utc_offset = 0f64;
-        let mut start_day = 1u32;
-        let mut end_day = 365u32;
+        let start_day = parsed.get_i64("start_day").map(|v| v as u32).unwrap_or(1u32);
+        let end_day = parsed.get_i64("end_day").map(|v| v as u32).unwrap_or(365u32);
         let mut start_time = NaiveTime::from_hms(0, 0, 0); // midnight
         let mut end_time = NaiveTime::from_hms(23, 59, 59); // the second before midnight
 
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
+        if let Some(value) = parsed.get_f64("lat") {
+            latitude = value as f32;
         }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
+        if let Some(value) = parsed.get_f64("long") {
+            longitude = value as f32;
+        }
+        if let Some(value) = parsed.get_string("utc_offset") {
+            let val = value
+                    .to_string()
+                    .replace("00", "0")
+                    .replace("+", "")
+                    .replace("UTC", "");
+            let val2: Vec<&str> = val.split(":").collect();
+
+            utc_offset = val2[0]
+                .parse::<f64>()
+                .expect("Error parsing --utc_offset");
+            if val2.len() > 1 {
+                utc_offset += val2[0]
+                    .parse::<f64>()
+                    .expect("Error parsing --utc_offset")
+                    / 60f64;
             }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-az_fraction" {
-                az_fraction = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-max_dist" {
-                max_dist = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-lat" || flag_val == "-latitude" {
-                latitude = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
+            if utc_offset < -12f64 || utc_offset > 12f64 {
+                panic!("The UTC offset must be between -12:00 and +12:00");
+            }
+        }
+        if let Some(value) = parsed.get_string("start_time") {
+            let val = value.to_string();
+            // The program is already confined to analyzing times between sunrise and sunset. Thus,
+            // the default setting for end_time of 00:00:00 is sufficient to work as sunrise. There
+            // is no need to parse this value.
+            if !val.to_lowercase().contains("sunrise") {
+                let time: Vec<&str> = val.split(":").collect();
+                let hr = time[0]
+                    .parse::<i32>()
+                    .expect("Error parsing --start_time");
+                let min = if time.len() > 1 {
+                    time[1]
+                        .parse::<i32>()
+                        .expect("Error parsing --start_time")
                 } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
+                    0i32
                 };
-            } else if flag_val == "-long" || flag_val == "-longitude" {
-                longitude = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
+                let sec = if time.len() > 2 {
+                    time[2]
+                        .parse::<i32>()
+                        .expect("Error parsing --start_time")
                 } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
+                    0i32
                 };
-            } else if flag_val == "-utc_offset" || flag_val == "-utc" {
-                let val = if keyval {
-                    vec[1]
-                        .to_string()
-                        .replace("00", "0")
-                        .replace("+", "")
-                        .replace("UTC", "")
+                if hr >= 0 && hr < 24 && min >= 0 && min < 60 && sec >= 0 && sec < 60 {
+                    start_time = NaiveTime::from_hms(hr as u32, min as u32, sec as u32);
                 } else {
-                    args[i + 1]
-                        .to_string()
-                        .replace("00", "0")
-                        .replace("+", "")
-                        .replace("UTC", "")
-                };
-                let val2: Vec<&str> = val.split(":").collect();
-
-                utc_offset = val2[0]
-                    .parse::<f64>()
-                    .expect(&format!("Error parsing {}", flag_val));
-                if val2.len() > 1 {
-                    utc_offset += val2[0]
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                        / 60f64;
-                }
-                if utc_offset < -12f64 || utc_offset > 12f64 {
-                    panic!("The UTC offset must be between -12:00 and +12:00");
+                    panic!("Invalid start time.");
                 }
-            } else if flag_val == "-start_day" {
-                start_day = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<u32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<u32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-end_day" {
-                end_day = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<u32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<u32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-start_time" {
-                let val = if keyval {
-                    vec[1].to_string()
+            }
+        }
+        if let Some(value) = parsed.get_string("end_time") {
+            let val = value.to_string();
+            // The program is already confined to analyzing times between sunrise and sunset. Thus,
+            // the default setting for end_time of 23:59:59 is sufficient to work as sunset. There
+            // is no need to parse this value.
+            if !val.to_lowercase().contains("sunset") {
+                let time: Vec<&str> = val.split(":").collect();
+                let hr = time[0]
+                    .parse::<i32>()
+                    .expect("Error parsing --end_time");
+                let min = if time.len() > 1 {
+                    time[1]
+                        .parse::<i32>()
+                        .expect("Error parsing --end_time")
                 } else {
-                    args[i + 1].to_string()
+                    0i32
                 };
-                // The program is already confined to analyzing times between sunrise and sunset. Thus,
-                // the default setting for end_time of 00:00:00 is sufficient to work as sunrise. There
-                // is no need to parse this value.
-                if !val.to_lowercase().contains("sunrise") {
-                    let time: Vec<&str> = val.split(":").collect();
-                    let hr = time[0]
+                let sec = if time.len() > 2 {
+                    time[2]
                         .parse::<i32>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                    let min = if time.len() > 1 {
-                        time[1]
-                            .parse::<i32>()
-                            .expect(&format!("Error parsing {}", flag_val))
-                    } else {
-                        0i32
-                    };
-                    let sec = if time.len() > 2 {
-                        time[2]
-                            .parse::<i32>()
-                            .expect(&format!("Error parsing {}", flag_val))
-                    } else {
-                        0i32
-                    };
-                    if hr >= 0 && hr < 24 && min >= 0 && min < 60 && sec >= 0 && sec < 60 {
-                        start_time = NaiveTime::from_hms(hr as u32, min as u32, sec as u32);
-                    } else {
-                        panic!("Invalid start time.");
-                    }
-                }
-            } else if flag_val == "-end_time" {
-                let val = if keyval {
-                    vec[1].to_string()
+                        .expect("Error parsing --end_time")
                 } else {
-                    args[i + 1].to_string()
+                    0i32
                 };
-                // The program is already confined to analyzing times between sunrise and sunset. Thus,
-                // the default setting for end_time of 23:59:59 is sufficient to work as sunset. There
-                // is no need to parse this value.
-                if !val.to_lowercase().contains("sunset") {
-                    let time: Vec<&str> = val.split(":").collect();
-                    let hr = time[0]
-                        .parse::<i32>()
-                        .expect(&format!("Error parsing {}", flag_val));
-                    let min = if time.len() > 1 {
-                        time[1]
-                            .parse::<i32>()
-                            .expect(&format!("Error parsing {}", flag_val))
-                    } else {
-                        0i32
-                    };
-                    let sec = if time.len() > 2 {
-                        time[2]
-                            .parse::<i32>()
-                            .expect(&format!("Error parsing {}", flag_val))
-                    } else {
-                        0i32
-                    };
-                    if hr >= 0 && hr < 24 && min >= 0 && min < 60 && sec >= 0 && sec < 60 {
-                        end_time = NaiveTime::from_hms(hr as u32, min as u32, sec as u32);
-                    } else {
-                        panic!("Invalid end time.");
-                    }
+                if hr >= 0 && hr < 24 && min >= 0 && min < 60 && sec >= 0 && sec < 60 {
+                    end_time = NaiveTime::from_hms(hr as u32, min as u32, sec as u32);
+                } else {
+                    panic!("Invalid end time.");
                 }
             }
         }
@@ -466,18 +358,12 @@ impl WhiteboxTool for TimeInDaylight {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let sep: String = path::MAIN_SEPARATOR.to_string();
-
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
 
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        output_file = resolve_path(&output_file, working_directory);
 
         if az_fraction <= 0f32 {
             panic!("The az_fraction parameter must be larger than zero.");
@@ -614,17 +500,13 @@ impl WhiteboxTool for TimeInDaylight {
                 };
 
                 // Now perform the filter
-                let mut num_procs = num_cpus::get() as isize;
                 let configs = whitebox_common::configs::get_configs()?;
-                let max_procs = configs.max_procs;
-                if max_procs > 0 && max_procs < num_procs {
-                    num_procs = max_procs;
-                }
+                let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
                 let (tx, rx) = mpsc::channel();
                 for tid in 0..num_procs {
                     let input = input.clone();
                     let tx = tx.clone();
-                    thread::spawn(move || {
+                    spawn(move || {
                         // The ray-tracing operation can be viewed as a linear maximum filter. The first step
                         // is to create the filter offsets and calculate the offset distances.
 
@@ -717,7 +599,7 @@ impl WhiteboxTool for TimeInDaylight {
                             let mut data: Vec<f32> = vec![nodata_f32; columns as usize];
                             for col in 0..columns {
                                 current_elev = input.get_value(row, col);
-                                if current_elev != nodata_f32 {
+                                if !is_nodata(current_elev as f64, nodata_f32 as f64) {
                                     // Run down the offsets of the ray
                                     current_max_slope = a_small_value;
                                     current_max_elev = a_small_value;
@@ -732,11 +614,11 @@ impl WhiteboxTool for TimeInDaylight {
                                         z1 = input.get_value(y1, x1);
                                         z2 = input.get_value(y2, x2);
 
-                                        if z1 == nodata_f32 && z2 == nodata_f32 {
+                                        if is_nodata(z1 as f64, nodata_f32 as f64) && is_nodata(z2 as f64, nodata_f32 as f64) {
                                             break; // We're likely off the grid.
-                                        } else if z1 == nodata_f32 {
+                                        } else if is_nodata(z1 as f64, nodata_f32 as f64) {
                                             z1 = z2;
-                                        } else if z2 == nodata_f32 {
+                                        } else if is_nodata(z2 as f64, nodata_f32 as f64) {
                                             z2 = z1;
                                         }
 
@@ -765,7 +647,9 @@ impl WhiteboxTool for TimeInDaylight {
                                     }
                                 }
                             }
-                            tx.send((row, data)).unwrap();
+                            if tx.send((row, data)).is_err() {
+                                return;
+                            }
                         }
                     });
                 }
@@ -773,10 +657,11 @@ impl WhiteboxTool for TimeInDaylight {
                 for row in 0..rows {
                     let (r, data) = rx.recv().expect("Error receiving data from thread.");
                     horizon_angle.set_row_data(r, data);
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress (Az={}): {}%", azimuth, progress);
+                            reporter.progress(&format!("Progress (Az={})", azimuth), progress);
                             old_progress = progress;
                         }
                     }
@@ -826,10 +711,11 @@ impl WhiteboxTool for TimeInDaylight {
 
                     output.increment_row_data(row, new_vals);
 
+                    reporter.check_cancelled()?;
                     if verbose {
                         progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                         if progress != old_progress {
-                            println!("Progress (Az={}): {}%", azimuth, progress);
+                            reporter.progress(&format!("Progress (Az={})", azimuth), progress);
                             old_progress = progress;
                         }
                     }
@@ -845,15 +731,16 @@ impl WhiteboxTool for TimeInDaylight {
             for row in 0..rows {
                 for col in 0..columns {
                     z = output.get_value(row, col);
-                    if z != nodata {
+                    if !is_nodata(z, nodata) {
                         // output.set_value(row, col, z / total_daylight_time);
                         output.set_value(row, col, 1f64 - (z / total_daylight));
                     }
                 }
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!("Progress: {}%", progress);
+                        reporter.progress("Progress", progress);
                         old_progress = progress;
                     }
                 }
@@ -6,18 +6,17 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use whitebox_vector::*;
-use num_cpus;
 use std::env;
 use std::f64;
 use std::io::{Error, ErrorKind};
 use std::path;
 use std::sync::mpsc;
 use std::sync::Arc;
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to calculate the viewshed (i.e. the visible area) from a
 /// location (i.e. viewing station) or group of locations based on the topography defined
@@ -61,7 +60,7 @@ impl Viewshed {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["--dem".to_owned()],
+            flags: vec!["-i".to_owned(), "--input".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -70,7 +69,7 @@ impl Viewshed {
 
         parameters.push(ToolParameter {
             name: "Viewing Station Vector File".to_owned(),
-            flags: vec!["--stations".to_owned()],
+            flags: vec!["--station".to_owned(), "--stations".to_owned()],
             description: "Input viewing station vector file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Vector(
                 VectorGeometryType::Point,
@@ -162,60 +161,13 @@ impl WhiteboxTool for Viewshed {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut stations_file = String::new();
-        let mut output_file = String::new();
-        let mut height = 2.0;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-stations" || flag_val == "-station" {
-                stations_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-height" {
-                height = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut stations_file = parsed.get_string("stations").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut height = parsed.get_f64("height").unwrap_or(2.0);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -228,27 +180,15 @@ impl WhiteboxTool for Viewshed {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-
-        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !stations_file.contains(&sep) && !stations_file.contains("/") {
-            stations_file = format!("{}{}", working_directory, stations_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        stations_file = resolve_input_path(&stations_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -307,11 +247,12 @@ impl WhiteboxTool for Viewshed {
             station_y.push(record.points[0].y);
             station_x.push(record.points[0].x);
 
+            reporter.check_cancelled()?;
             if verbose {
                 progress =
                     (100.0_f64 * record_num as f64 / (stations.num_records - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Locating view stations: {}%", progress);
+                    reporter.progress("Locating view stations", progress);
                     old_progress = progress;
                 }
             }
@@ -345,7 +286,7 @@ impl WhiteboxTool for Viewshed {
             for tid in 0..num_procs {
                 let dem = dem.clone();
                 let tx = tx.clone();
-                thread::spawn(move || {
+                spawn(move || {
                     let (mut x, mut y): (f64, f64);
                     let mut z: f64;
                     let mut dz: f64;
@@ -354,7 +295,7 @@ impl WhiteboxTool for Viewshed {
                         let mut data: Vec<f32> = vec![-32768f32; columns as usize];
                         for col in 0..columns {
                             z = dem.get_value(row, col);
-                            if z != nodata {
+                            if !is_nodata(z, nodata) {
                                 x = dem.get_x_from_column(col);
                                 y = dem.get_y_from_row(row);
                                 dz = z - stn_z;
@@ -367,7 +308,9 @@ impl WhiteboxTool for Viewshed {
                                 }
                             }
                         }
-                        tx.send((row, data)).unwrap();
+                        if tx.send((row, data)).is_err() {
+                            return;
+                        }
                     }
                 });
             }
@@ -376,13 +319,11 @@ impl WhiteboxTool for Viewshed {
                 let (row, data) = rx.recv().expect("Error receiving data from thread.");
                 view_angle.set_row_data(row, data);
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * r as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Calculating view angle (Station {} of {}): {}%",
-                            stn_num, num_stn, progress
-                        );
+                        reporter.progress(&format!("Calculating view angle (Station {} of {})", stn_num, num_stn), progress);
                         old_progress = progress;
                     }
                 }
@@ -666,7 +607,7 @@ impl WhiteboxTool for Viewshed {
                     // } else {
                     //     output.set_value(row, col, nodata);
                     // }
-                    if dem.get_value(row, col) != nodata {
+                    if !is_nodata(dem.get_value(row, col), nodata) {
                         value = if max_view_angle.get_value(row, col)
                             > view_angle.get_value(row, col)
                         {
@@ -678,13 +619,11 @@ impl WhiteboxTool for Viewshed {
                     }
                 }
 
+                reporter.check_cancelled()?;
                 if verbose {
                     progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                     if progress != old_progress {
-                        println!(
-                            "Creating output: (Station {} of {}): {}%",
-                            stn_num, num_stn, progress
-                        );
+                        reporter.progress(&format!("Creating output: (Station {} of {})", stn_num, num_stn), progress);
                         old_progress = progress;
                     }
                 }
@@ -6,18 +6,17 @@ Last Modified: 12/10/2018
 License: MIT
 */
 
-use whitebox_raster::Raster;
+use whitebox_raster::{is_nodata, Raster};
 use whitebox_common::structures::Array2D;
 use crate::tools::*;
 use whitebox_common::utils::get_formatted_elapsed_time;
-use num_cpus;
 use std::env;
 use std::f64;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::path;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
-use std::thread;
+use whitebox_common::utils::spawn;
 
 /// This tool can be used to calculate a measure of landscape visibility based on the
 /// topography of an input digital elevation model (DEM). The user must specify the name of
@@ -61,7 +60,7 @@ impl VisibilityIndex {
         let mut parameters = vec![];
         parameters.push(ToolParameter {
             name: "Input DEM File".to_owned(),
-            flags: vec!["--dem".to_owned()],
+            flags: vec!["-i".to_owned(), "--input".to_owned(), "--dem".to_owned()],
             description: "Input raster DEM file.".to_owned(),
             parameter_type: ParameterType::ExistingFile(ParameterFileType::Raster),
             default_value: None,
@@ -161,66 +160,13 @@ impl WhiteboxTool for VisibilityIndex {
         args: Vec<String>,
         working_directory: &'a str,
         verbose: bool,
+        reporter: &dyn ProgressReporter,
     ) -> Result<(), Error> {
-        let mut input_file = String::new();
-        let mut output_file = String::new();
-        let mut height = 2f32;
-        let mut res_factor = 2isize;
-
-        if args.len() == 0 {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "Tool run with no parameters.",
-            ));
-        }
-        for i in 0..args.len() {
-            let mut arg = args[i].replace("\"", "");
-            arg = arg.replace("\'", "");
-            let cmd = arg.split("="); // in case an equals sign was used
-            let vec = cmd.collect::<Vec<&str>>();
-            let mut keyval = false;
-            if vec.len() > 1 {
-                keyval = true;
-            }
-            let flag_val = vec[0].to_lowercase().replace("--", "-");
-            if flag_val == "-i" || flag_val == "-input" || flag_val == "-dem" {
-                input_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-o" || flag_val == "-output" {
-                output_file = if keyval {
-                    vec[1].to_string()
-                } else {
-                    args[i + 1].to_string()
-                };
-            } else if flag_val == "-height" {
-                height = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f32>()
-                        .expect(&format!("Error parsing {}", flag_val))
-                };
-            } else if flag_val == "-res_factor" {
-                res_factor = if keyval {
-                    vec[1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as isize
-                } else {
-                    args[i + 1]
-                        .to_string()
-                        .parse::<f64>()
-                        .expect(&format!("Error parsing {}", flag_val)) as isize
-                };
-            }
-        }
+        let parsed = parse_args(&self.parameters, &args)?;
+        let mut input_file = parsed.get_string("dem").unwrap_or_default();
+        let mut output_file = parsed.get_string("output").unwrap_or_default();
+        let mut height = parsed.get_f64("height").map(|v| v as f32).unwrap_or(2f32);
+        let mut res_factor = parsed.get_i64("res_factor").map(|v| v as isize).unwrap_or(2isize);
 
         if verbose {
             let tool_name = self.get_tool_name();
@@ -233,24 +179,14 @@ impl WhiteboxTool for VisibilityIndex {
             println!("{}", "*".repeat(welcome_len));
         }
 
-        let mut num_procs = num_cpus::get() as isize;
         let configs = whitebox_common::configs::get_configs()?;
-        let max_procs = configs.max_procs;
-        if max_procs > 0 && max_procs < num_procs {
-            num_procs = max_procs;
-        }
-
-        let sep: String = path::MAIN_SEPARATOR.to_string();
+        let num_procs = whitebox_common::utils::ThreadPool::new(&configs).num_threads() as isize;
 
         let mut progress: usize;
         let mut old_progress: usize = 1;
 
-        if !input_file.contains(&sep) && !input_file.contains("/") {
-            input_file = format!("{}{}", working_directory, input_file);
-        }
-        if !output_file.contains(&sep) && !output_file.contains("/") {
-            output_file = format!("{}{}", working_directory, output_file);
-        }
+        input_file = resolve_input_path(&input_file, working_directory)?;
+        output_file = resolve_path(&output_file, working_directory);
 
         if verbose {
             println!("Reading data...")
@@ -284,11 +220,13 @@ impl WhiteboxTool for VisibilityIndex {
         let target_cells_count = (num_cells_tested / 100f64) as usize;
         let num_cells_completed = Arc::new(Mutex::new(0));
         let (tx, rx) = mpsc::channel();
+        let token = reporter.cancellation_token();
         for tid in 0..num_procs {
             let dem = dem.clone();
             let num_cells_completed = num_cells_completed.clone();
             let tx = tx.clone();
-            thread::spawn(move || {
+            let token = token.clone();
+            spawn(move || {
                 let mut return_data: Array2D<usize> =
                     Array2D::new(rows, columns, 0usize, 0usize).unwrap();
                 let mut view_angle: Array2D<f32> =
@@ -299,6 +237,9 @@ impl WhiteboxTool for VisibilityIndex {
                 let mut row_y_values = vec![0f32; rows as usize];
                 let mut col_x_values = vec![0f32; columns as usize];
                 for row in 0..rows {
+                    if token.is_cancelled() {
+                        break;
+                    }
                     row_y_values[row as usize] = dem.get_y_from_row(row) as f32;
                 }
                 for col in 0..columns {
@@ -624,7 +565,9 @@ impl WhiteboxTool for VisibilityIndex {
                                 let progress = (100.0_f64 * *num_cells_completed as f64
                                     / (num_cells_tested - 1f64))
                                     as usize;
-                                println!("Progress (Loop 1 of 2): {}%", progress);
+                                if tx.send(WorkerMessage::Progress("Progress (Loop 1 of 2)", progress)).is_err() {
+                                    return;
+                                }
                             }
                         }
                     }
@@ -634,37 +577,43 @@ impl WhiteboxTool for VisibilityIndex {
                 if verbose {
                     let progress = (100.0_f64 * *num_cells_completed as f64
                         / (num_cells_tested - 1f64)) as usize;
-                    println!("Progress (Loop 1 of 2): {}%", progress);
+                    if tx.send(WorkerMessage::Progress("Progress (Loop 1 of 2)", progress)).is_err() {
+                        return;
+                    }
+                }
+                if tx.send(WorkerMessage::Data(return_data)).is_err() {
+                    return;
                 }
-                tx.send(return_data).unwrap();
             });
         }
 
         let mut output = Raster::initialize_using_file(&output_file, &dem);
         let mut z: f64;
         for _p in 0..num_procs {
-            let data = rx.recv().expect("Error receiving data from thread.");
+            let data = recv_data(&rx, reporter).expect("Error receiving data from thread.");
             for row in 0..rows {
                 for col in 0..columns {
-                    if dem.get_value(row, col) != nodata {
+                    if !is_nodata(dem.get_value(row, col), nodata) {
                         z = data.get_value(row, col) as f64;
                         output.increment(row, col, z);
                     }
                 }
             }
         }
+        reporter.check_cancelled()?;
 
         for row in 0..rows {
             for col in 0..columns {
-                if dem.get_value(row, col) != nodata {
+                if !is_nodata(dem.get_value(row, col), nodata) {
                     z = output.get_value(row, col);
                     output.set_value(row, col, z / num_cells_tested)
                 }
             }
+            reporter.check_cancelled()?;
             if verbose {
                 progress = (100.0_f64 * row as f64 / (rows - 1) as f64) as usize;
                 if progress != old_progress {
-                    println!("Progress (Loop 2 of 2): {}%", progress);
+                    reporter.progress("Progress (Loop 2 of 2)", progress);
                     old_progress = progress;
                 }
             }
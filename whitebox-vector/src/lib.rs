@@ -8,6 +8,7 @@ License: MIT
 
 // private sub-module defined in other files
 pub mod shapefile;
+mod vector_provider;
 
 // exports identifiers from private sub-modules in the current module namespace
 // pub use self::shapefile::attributes::{
@@ -18,4 +19,7 @@ pub use crate::shapefile::attributes::*;
 pub use crate::shapefile::geometry::*;
 pub use crate::shapefile::geometry::ShapeType;
 pub use crate::shapefile::Shapefile;
+pub use crate::vector_provider::{
+    register_vector_provider, unregister_vector_provider, MemoryVectorProvider, VectorProvider,
+};
 // pub use whitebox_common::structures::Point2D;
@@ -10,16 +10,22 @@ Notes: The logic behind working with the ESRI Shapefile format.
 
 pub mod attributes;
 pub mod geometry;
+mod postgis;
+mod spatialite;
+mod wkb;
 
 use self::attributes::*;
 use self::geometry::*;
+use crate::vector_provider::vector_provider;
 use whitebox_common::structures::Point2D;
-use whitebox_common::utils::{ByteOrderReader, Endianness};
+use whitebox_common::utils::{
+    is_postgis_dataset, is_sqlite_dataset, open_input, read_input, time_io, write_atomically,
+    ByteOrderReader, Endianness,
+};
 use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
 use chrono::prelude::*;
 use std::f64;
 use std::fmt;
-use std::fs;
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::{BufReader, BufWriter, Cursor, Error, ErrorKind};
@@ -110,12 +116,30 @@ pub struct Shapefile {
 
 impl Shapefile {
     pub fn read<'a>(file_name: &'a str) -> Result<Shapefile, Error> {
+        if let Some(provider) = vector_provider(file_name) {
+            let mut sf = provider.read(file_name)?;
+            sf.file_name = file_name.to_string();
+            sf.file_mode = "r".to_string();
+            return Ok(sf);
+        }
         let mut sf = Shapefile {
             file_name: file_name.to_string(),
             file_mode: "r".to_string(),
             ..Default::default()
         };
-        sf.read_file()?;
+        if is_postgis_dataset(file_name) {
+            time_io(|| postgis::read_table(&mut sf))?;
+        } else if is_sqlite_dataset(file_name) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "SQLite databases can be written but not read as vector inputs ({}).",
+                    file_name
+                ),
+            ));
+        } else {
+            time_io(|| sf.read_file())?;
+        }
         Ok(sf)
     }
 
@@ -234,14 +258,9 @@ impl Shapefile {
         // First read the geometries //
         ///////////////////////////////
 
-        // read the header
-        let mut f = File::open(self.file_name.clone()).unwrap(); //?;
-        let metadata = fs::metadata(self.file_name.clone()).unwrap(); //?;
-        let file_size: usize = metadata.len() as usize;
-        let mut buffer = vec![0; file_size];
-
-        // read the file's bytes into a buffer
-        f.read(&mut buffer).unwrap();
+        // read the file's bytes into a buffer; the file may also be in an object store
+        let buffer = read_input(&self.file_name)?;
+        let file_size = buffer.len();
 
         // Note: the shapefile format uses mixed endianness for whatever reason.
         // The ByteOrderReader was set up to have one consistent endianness. As
@@ -555,7 +574,7 @@ impl Shapefile {
             .into_os_string()
             .into_string()
             .unwrap();
-        match File::open(prj_file) {
+        match open_input(&prj_file) {
             Ok(f) => {
                 let f = BufReader::new(f);
                 for line in f.lines() {
@@ -576,13 +595,7 @@ impl Shapefile {
             .into_os_string()
             .into_string()
             .unwrap();
-        let mut f = File::open(dbf_file.clone()).unwrap();
-        let metadata = fs::metadata(dbf_file.clone()).unwrap();
-        let file_size: usize = metadata.len() as usize;
-        let mut buffer = vec![0; file_size];
-
-        // read the file's bytes into a buffer
-        f.read(&mut buffer).unwrap();
+        let buffer = read_input(&dbf_file)?;
         let mut bor =
             ByteOrderReader::<Cursor<Vec<u8>>>::new(Cursor::new(buffer), Endianness::LittleEndian);
 
@@ -727,6 +740,32 @@ impl Shapefile {
             ));
         }
 
+        if is_postgis_dataset(&self.file_name) {
+            return time_io(|| postgis::write_table(self));
+        }
+        if let Some(provider) = vector_provider(&self.file_name) {
+            self.calculate_extent();
+            return provider.write(&self.file_name, self);
+        }
+
+        // The .shp, .shx, .dbf, and .prj files, or the SpatiaLite database, are written to a
+        // temporary directory and only moved into place once all of them are complete.
+        let file_name = self.file_name.clone();
+        let result = time_io(|| {
+            write_atomically(&file_name, |temp_file| {
+                self.file_name = temp_file.to_string();
+                if is_sqlite_dataset(&file_name) {
+                    spatialite::write_database(self)
+                } else {
+                    self.write_files()
+                }
+            })
+        });
+        self.file_name = file_name;
+        result
+    }
+
+    fn write_files(&mut self) -> Result<(), Error> {
         /////////////////////////////////////////
         // Write the geometry data (.shp file) //
         /////////////////////////////////////////
@@ -0,0 +1,695 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Reading and writing vector datasets stored as tables in a PostGIS database. A dataset is
+named 'postgis:schema.table', or 'postgis:table' for a table in the public schema, and the
+database is given by the postgis_connection setting, a libpq-style connection string (e.g.
+'host=localhost dbname=gis user=postgres'). The driver requires a build with the 'postgis'
+feature.
+
+Geometries are exchanged as well-known binary (WKB). When reading, the rows of a table are
+converted to a single shape type: points become a Point (or MultiPoint, if any row holds a
+multi-point) Shapefile, line strings a PolyLine, and polygons a Polygon Shapefile, with exterior
+rings in clockwise and holes in counter-clockwise order, following the Shapefile convention. Rows
+with null or empty geometries are skipped. Z values give a Z shape type (with zero measures, if
+the table has none) and measures alone an M shape type. Attribute columns of integer, numeric,
+boolean, and date types keep their types and all other columns are read as text; a table
+without attribute columns is given an FID field.
+
+When writing, any existing table of the same name is replaced, within a single transaction,
+unless the no_overwrite setting is enabled. The table has a serial 'fid' primary key (unless
+the Shapefile has an FID field), the attribute fields, and a 'geom' column (POINT, MULTIPOINT, MULTILINESTRING, or MULTIPOLYGON)
+with a GIST index. Rows are inserted in chunks of up to 1000 records. The SRID is taken from
+the last EPSG authority code of the projection, or 0 if there is none. Z shape types are
+written with measures only if any of their records holds measures.
+*/
+
+#[cfg(not(feature = "postgis"))]
+use super::Shapefile;
+use std::io::Error;
+#[cfg(feature = "postgis")]
+use std::io::ErrorKind;
+
+#[cfg(feature = "postgis")]
+pub(super) use self::driver::{read_table, write_table};
+
+#[cfg(not(feature = "postgis"))]
+pub(super) fn read_table(_sf: &mut Shapefile) -> Result<(), Error> {
+    Err(not_supported())
+}
+
+#[cfg(not(feature = "postgis"))]
+pub(super) fn write_table(_sf: &mut Shapefile) -> Result<(), Error> {
+    Err(not_supported())
+}
+
+#[cfg(not(feature = "postgis"))]
+fn not_supported() -> Error {
+    Error::other(
+        "This build of WhiteboxTools does not support PostGIS datasets; it must be built with the 'postgis' feature (cargo build --release --features postgis).",
+    )
+}
+
+/// Returns the schema and table names of a dataset named 'postgis:schema.table'.
+#[cfg(feature = "postgis")]
+fn table_name(file_name: &str) -> Result<(String, String), Error> {
+    let name = file_name.trim();
+    let name = &name["postgis:".len()..];
+    // Shapefile::new appends a .shp extension to names without one
+    let name = if name.to_lowercase().ends_with(".shp") {
+        &name[..name.len() - 4]
+    } else {
+        name
+    };
+    let (schema, table) = match name.find('.') {
+        Some(i) => (&name[..i], &name[i + 1..]),
+        None => ("public", name),
+    };
+    if schema.is_empty() || table.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("The PostGIS dataset name {} must be given as postgis:schema.table or postgis:table.", file_name),
+        ));
+    }
+    Ok((schema.to_string(), table.to_string()))
+}
+
+#[cfg(feature = "postgis")]
+mod driver {
+    use super::super::attributes::*;
+    use super::super::geometry::*;
+    use super::super::wkb::*;
+    use super::super::Shapefile;
+    use super::table_name;
+    use postgres::types::ToSql;
+    use postgres::{Client, NoTls};
+    use std::io::{Error, ErrorKind};
+    use whitebox_common::algorithms::is_clockwise_order;
+    use whitebox_common::configs::get_configs;
+    use whitebox_common::spatial_ref_system::esri_wkt_from_epsg;
+    use whitebox_common::structures::Point2D;
+
+    /// The maximum number of records inserted by a single statement.
+    const INSERT_CHUNK_SIZE: usize = 1000;
+
+    const WKB_GEOMETRYCOLLECTION: u32 = 7;
+
+    /// The byte that begins each geometry of standard WKB, i.e. its (little-endian) byte order.
+    const WKB_ENTITY: u8 = 1;
+
+    // flags of the extended WKB used by PostGIS
+    const EWKB_Z: u32 = 0x8000_0000;
+    const EWKB_M: u32 = 0x4000_0000;
+    const EWKB_SRID: u32 = 0x2000_0000;
+
+    fn db_error(e: postgres::Error) -> Error {
+        let msg = match e.as_db_error() {
+            Some(db) => db.message().to_string(),
+            None => e.to_string(),
+        };
+        Error::other(format!("Error accessing the PostGIS database: {}", msg))
+    }
+
+    fn connect() -> Result<Client, Error> {
+        let conn = get_configs()?.postgis_connection;
+        if conn.trim().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "No PostGIS connection has been set; use the --postgis_connection flag, e.g. --postgis_connection=\"host=localhost dbname=gis user=postgres\".",
+            ));
+        }
+        Client::connect(&conn, NoTls).map_err(db_error)
+    }
+
+    fn quote_ident(name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    /// A ring, line, or point of a decoded geometry.
+    struct Part {
+        points: Vec<Point2D>,
+        z: Vec<f64>,
+        m: Vec<f64>,
+        /// Whether a polygon ring is an interior ring (hole).
+        is_hole: bool,
+    }
+
+    /// A geometry decoded from WKB, flattened into its parts.
+    struct Geometry {
+        /// Point, MultiPoint, PolyLine, or Polygon
+        base_type: ShapeType,
+        has_z: bool,
+        has_m: bool,
+        parts: Vec<Part>,
+    }
+
+    struct WkbReader<'a> {
+        data: &'a [u8],
+        pos: usize,
+        little_endian: bool,
+    }
+
+    impl<'a> WkbReader<'a> {
+        fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+            if self.pos + N > self.data.len() {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    "A geometry read from the PostGIS database is truncated.",
+                ));
+            }
+            let mut a = [0u8; N];
+            a.copy_from_slice(&self.data[self.pos..self.pos + N]);
+            self.pos += N;
+            Ok(a)
+        }
+
+        fn read_u32(&mut self) -> Result<u32, Error> {
+            let a = self.read_bytes::<4>()?;
+            Ok(if self.little_endian {
+                u32::from_le_bytes(a)
+            } else {
+                u32::from_be_bytes(a)
+            })
+        }
+
+        fn read_f64(&mut self) -> Result<f64, Error> {
+            let a = self.read_bytes::<8>()?;
+            Ok(if self.little_endian {
+                f64::from_le_bytes(a)
+            } else {
+                f64::from_be_bytes(a)
+            })
+        }
+
+        /// Reads a geometry and appends its parts. Returns the base shape type of the geometry.
+        fn read_geometry(&mut self, geom: &mut Geometry) -> Result<ShapeType, Error> {
+            self.little_endian = self.read_bytes::<1>()?[0] == 1;
+            let code = self.read_u32()?;
+            let mut has_z = code & EWKB_Z != 0;
+            let mut has_m = code & EWKB_M != 0;
+            if code & EWKB_SRID != 0 {
+                self.read_u32()?;
+            }
+            // ISO WKB adds 1000 for Z, 2000 for M, and 3000 for ZM coordinates
+            let iso = code & 0x0fff_ffff;
+            match iso / 1000 {
+                1 => has_z = true,
+                2 => has_m = true,
+                3 => {
+                    has_z = true;
+                    has_m = true;
+                }
+                _ => {}
+            }
+            geom.has_z |= has_z;
+            geom.has_m |= has_m;
+            match iso % 1000 {
+                WKB_POINT => {
+                    let part = self.read_points(1, has_z, has_m)?;
+                    // an empty point is written with NaN coordinates
+                    if !part.points[0].x.is_nan() {
+                        geom.parts.push(part);
+                    }
+                    Ok(ShapeType::Point)
+                }
+                WKB_LINESTRING => {
+                    let n = self.read_u32()? as usize;
+                    let part = self.read_points(n, has_z, has_m)?;
+                    if n > 0 {
+                        geom.parts.push(part);
+                    }
+                    Ok(ShapeType::PolyLine)
+                }
+                WKB_POLYGON => {
+                    let num_rings = self.read_u32()?;
+                    for ring in 0..num_rings {
+                        let n = self.read_u32()? as usize;
+                        let mut part = self.read_points(n, has_z, has_m)?;
+                        part.is_hole = ring > 0;
+                        if n > 0 {
+                            geom.parts.push(part);
+                        }
+                    }
+                    Ok(ShapeType::Polygon)
+                }
+                WKB_MULTIPOINT | WKB_MULTILINESTRING | WKB_MULTIPOLYGON => {
+                    let num_members = self.read_u32()?;
+                    for _ in 0..num_members {
+                        self.read_geometry(geom)?;
+                    }
+                    Ok(match iso % 1000 {
+                        WKB_MULTIPOINT => ShapeType::MultiPoint,
+                        WKB_MULTILINESTRING => ShapeType::PolyLine,
+                        _ => ShapeType::Polygon,
+                    })
+                }
+                WKB_GEOMETRYCOLLECTION => {
+                    let num_members = self.read_u32()?;
+                    let mut base_type = ShapeType::Null;
+                    for _ in 0..num_members {
+                        let member_type = self.read_geometry(geom)?;
+                        base_type = unify_shape_types(base_type, member_type)?;
+                    }
+                    Ok(base_type)
+                }
+                t => Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("The WKB geometry type {} is not supported.", t),
+                )),
+            }
+        }
+
+        fn read_points(&mut self, n: usize, has_z: bool, has_m: bool) -> Result<Part, Error> {
+            let mut part = Part {
+                points: Vec::with_capacity(n),
+                z: vec![],
+                m: vec![],
+                is_hole: false,
+            };
+            for _ in 0..n {
+                let x = self.read_f64()?;
+                let y = self.read_f64()?;
+                part.points.push(Point2D::new(x, y));
+                if has_z {
+                    part.z.push(self.read_f64()?);
+                }
+                if has_m {
+                    part.m.push(self.read_f64()?);
+                }
+            }
+            Ok(part)
+        }
+    }
+
+    fn decode_wkb(data: &[u8]) -> Result<Geometry, Error> {
+        let mut geom = Geometry {
+            base_type: ShapeType::Null,
+            has_z: false,
+            has_m: false,
+            parts: vec![],
+        };
+        let mut reader = WkbReader {
+            data,
+            pos: 0,
+            little_endian: true,
+        };
+        geom.base_type = reader.read_geometry(&mut geom)?;
+        Ok(geom)
+    }
+
+    /// Returns the shape type that can hold geometries of both base types.
+    fn unify_shape_types(a: ShapeType, b: ShapeType) -> Result<ShapeType, Error> {
+        match (a, b) {
+            (ShapeType::Null, t) | (t, ShapeType::Null) => Ok(t),
+            (a, b) if a == b => Ok(a),
+            (ShapeType::Point, ShapeType::MultiPoint) | (ShapeType::MultiPoint, ShapeType::Point) => {
+                Ok(ShapeType::MultiPoint)
+            }
+            _ => Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("The PostGIS table mixes {} and {} geometries, which cannot be held in a single Shapefile.", a, b),
+            )),
+        }
+    }
+
+    fn shape_type_with_dimension(base_type: ShapeType, has_z: bool, has_m: bool) -> ShapeType {
+        match (base_type, has_z, has_m) {
+            (ShapeType::Point, true, _) => ShapeType::PointZ,
+            (ShapeType::Point, false, true) => ShapeType::PointM,
+            (ShapeType::MultiPoint, true, _) => ShapeType::MultiPointZ,
+            (ShapeType::MultiPoint, false, true) => ShapeType::MultiPointM,
+            (ShapeType::PolyLine, true, _) => ShapeType::PolyLineZ,
+            (ShapeType::PolyLine, false, true) => ShapeType::PolyLineM,
+            (ShapeType::Polygon, true, _) => ShapeType::PolygonZ,
+            (ShapeType::Polygon, false, true) => ShapeType::PolygonM,
+            (t, _, _) => t,
+        }
+    }
+
+    /// Converts a decoded geometry into a record of the given shape type.
+    fn to_record(mut geom: Geometry, shape_type: ShapeType) -> ShapefileGeometry {
+        let mut sfg = ShapefileGeometry::new(shape_type);
+        let dimension = shape_type.dimension();
+        for part in &mut geom.parts {
+            let n = part.points.len();
+            if part.z.len() != n {
+                part.z = vec![0f64; n];
+            }
+            if part.m.len() != n {
+                part.m = vec![0f64; n];
+            }
+            // exterior rings are clockwise and holes counter-clockwise in Shapefiles
+            if shape_type.base_shape_type() == ShapeType::Polygon
+                && is_clockwise_order(&part.points) == part.is_hole
+            {
+                part.points.reverse();
+                part.z.reverse();
+                part.m.reverse();
+            }
+            match shape_type.base_shape_type() {
+                ShapeType::Point | ShapeType::MultiPoint => {
+                    for i in 0..n {
+                        match dimension {
+                            ShapeTypeDimension::Z => {
+                                sfg.add_pointz(part.points[i], part.m[i], part.z[i])
+                            }
+                            ShapeTypeDimension::Measure => sfg.add_pointm(part.points[i], part.m[i]),
+                            ShapeTypeDimension::XY => sfg.add_point(part.points[i]),
+                        }
+                    }
+                }
+                _ => match dimension {
+                    ShapeTypeDimension::Z => sfg.add_partz(&part.points, &part.m, &part.z),
+                    ShapeTypeDimension::Measure => sfg.add_partm(&part.points, &part.m),
+                    ShapeTypeDimension::XY => sfg.add_part(&part.points),
+                },
+            }
+        }
+        sfg
+    }
+
+    /// An attribute column of a PostGIS table.
+    struct Column {
+        name: String,
+        field: AttributeField,
+        // the length of text columns without a maximum length is set from their values
+        variable_length: bool,
+    }
+
+    fn column_field(
+        name: &str,
+        data_type: &str,
+        max_length: Option<i32>,
+        precision: Option<i32>,
+        scale: Option<i32>,
+    ) -> (AttributeField, bool) {
+        let field = match data_type {
+            "smallint" => AttributeField::new(name, FieldDataType::Int, 6u8, 0u8),
+            "integer" => AttributeField::new(name, FieldDataType::Int, 11u8, 0u8),
+            "bigint" => AttributeField::new(name, FieldDataType::Real, 20u8, 0u8),
+            "numeric" => {
+                let scale = scale.unwrap_or(6).clamp(0, 15);
+                let length = precision.map(|p| p + 2).unwrap_or(20).clamp(scale + 2, 254);
+                AttributeField::new(name, FieldDataType::Real, length as u8, scale as u8)
+            }
+            "real" | "double precision" => AttributeField::new(name, FieldDataType::Real, 18u8, 6u8),
+            "boolean" => AttributeField::new(name, FieldDataType::Bool, 1u8, 0u8),
+            "date" => AttributeField::new(name, FieldDataType::Date, 8u8, 0u8),
+            _ => {
+                let length = max_length.unwrap_or(1).clamp(1, 254);
+                return (
+                    AttributeField::new(name, FieldDataType::Text, length as u8, 0u8),
+                    max_length.is_none(),
+                );
+            }
+        };
+        (field, false)
+    }
+
+    fn parse_value(value: Option<String>, field: &AttributeField) -> FieldData {
+        let value = match value {
+            Some(v) => v,
+            None => return FieldData::Null,
+        };
+        match field.field_type {
+            'N' => value.parse::<i32>().map(FieldData::Int).unwrap_or(FieldData::Null),
+            'F' => value.parse::<f64>().map(FieldData::Real).unwrap_or(FieldData::Null),
+            'L' => FieldData::Bool(value.starts_with('t')),
+            'D' if value.len() == 8 => FieldData::Date(DateData {
+                year: value[0..4].parse::<u16>().unwrap_or(0),
+                month: value[4..6].parse::<u8>().unwrap_or(0),
+                day: value[6..8].parse::<u8>().unwrap_or(0),
+            }),
+            'D' => FieldData::Null,
+            _ => FieldData::Text(value),
+        }
+    }
+
+    pub fn read_table(sf: &mut Shapefile) -> Result<(), Error> {
+        let (schema, table) = table_name(&sf.file_name)?;
+        let mut client = connect()?;
+
+        let rows = client
+            .query(
+                "SELECT f_geometry_column::text, srid::integer FROM geometry_columns WHERE f_table_schema = $1 AND f_table_name = $2",
+                &[&schema, &table],
+            )
+            .map_err(db_error)?;
+        if rows.is_empty() {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("The PostGIS table {}.{} does not exist or has no geometry column.", schema, table),
+            ));
+        }
+        let geom_column: String = rows[0].get(0);
+        let srid: i32 = rows[0].get(1);
+
+        let mut columns = vec![];
+        for row in client
+            .query(
+                "SELECT column_name::text, data_type::text, character_maximum_length::integer, numeric_precision::integer, numeric_scale::integer, udt_name::text FROM information_schema.columns WHERE table_schema = $1 AND table_name = $2 ORDER BY ordinal_position",
+                &[&schema, &table],
+            )
+            .map_err(db_error)?
+        {
+            let name: String = row.get(0);
+            let udt_name: String = row.get(5);
+            if name == geom_column || udt_name == "geometry" || udt_name == "geography" {
+                continue;
+            }
+            let data_type: String = row.get(1);
+            let (field, variable_length) =
+                column_field(&name, &data_type, row.get(2), row.get(3), row.get(4));
+            columns.push(Column {
+                name,
+                field,
+                variable_length,
+            });
+        }
+
+        let mut select = vec![format!("ST_AsBinary({})", quote_ident(&geom_column))];
+        for c in &columns {
+            select.push(match c.field.field_type {
+                'D' => format!("to_char({}, 'YYYYMMDD')", quote_ident(&c.name)),
+                _ => format!("{}::text", quote_ident(&c.name)),
+            });
+        }
+        let sql = format!(
+            "SELECT {} FROM {}.{}",
+            select.join(", "),
+            quote_ident(&schema),
+            quote_ident(&table)
+        );
+        let mut geometries = vec![];
+        let mut records = vec![];
+        let mut base_type = ShapeType::Null;
+        let (mut has_z, mut has_m) = (false, false);
+        for row in client.query(sql.as_str(), &[]).map_err(db_error)? {
+            let wkb: Option<Vec<u8>> = row.get(0);
+            let geom = match wkb {
+                Some(wkb) => decode_wkb(&wkb)?,
+                None => continue,
+            };
+            if geom.parts.is_empty() {
+                continue;
+            }
+            base_type = unify_shape_types(base_type, geom.base_type)?;
+            has_z |= geom.has_z;
+            has_m |= geom.has_m;
+            let mut rec = Vec::with_capacity(columns.len());
+            for (i, c) in columns.iter_mut().enumerate() {
+                let value: Option<String> = row.get(i + 1);
+                if c.variable_length {
+                    if let Some(v) = &value {
+                        let len = v.len().clamp(c.field.field_length as usize, 254);
+                        c.field.field_length = len as u8;
+                    }
+                }
+                rec.push(parse_value(value, &c.field));
+            }
+            geometries.push(geom);
+            records.push(rec);
+        }
+        if geometries.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("The PostGIS table {}.{} does not contain any geometries.", schema, table),
+            ));
+        }
+
+        let shape_type = shape_type_with_dimension(base_type, has_z, has_m);
+        sf.header.shape_type = shape_type;
+        for geom in geometries {
+            sf.records.push(to_record(geom, shape_type));
+        }
+        sf.num_records = sf.records.len();
+        if columns.is_empty() {
+            // as with other outputs, a table without attributes is given an FID field
+            sf.attributes
+                .add_field(&AttributeField::new("FID", FieldDataType::Int, 11u8, 0u8));
+            for i in 0..records.len() {
+                sf.attributes.add_record(vec![FieldData::Int(i as i32 + 1)], false);
+            }
+        } else {
+            for c in &columns {
+                sf.attributes.add_field(&c.field);
+            }
+            for rec in records {
+                sf.attributes.add_record(rec, false);
+            }
+        }
+        sf.calculate_extent();
+
+        if srid > 0 {
+            let wkt = esri_wkt_from_epsg(srid as u16);
+            sf.projection = if wkt != "Unknown EPSG Code" {
+                wkt
+            } else {
+                client
+                    .query("SELECT srtext::text FROM spatial_ref_sys WHERE srid = $1", &[&srid])
+                    .map_err(db_error)?
+                    .first()
+                    .and_then(|row| row.get::<_, Option<String>>(0))
+                    .unwrap_or_default()
+            };
+        }
+        Ok(())
+    }
+
+    pub fn write_table(sf: &mut Shapefile) -> Result<(), Error> {
+        let (schema, table) = table_name(&sf.file_name)?;
+        let shape_type = sf.header.shape_type;
+        let (geom_type, _) = geometry_type(shape_type).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "Shapefiles of the Null shape type cannot be written to PostGIS.",
+            )
+        })?;
+        let (has_z, has_m) = output_dimensions(sf);
+        let (geom_type, dimension) = match (has_z, has_m) {
+            (true, true) => (geom_type.to_string(), 4),
+            (true, false) => (geom_type.to_string(), 3),
+            (false, true) => (format!("{}M", geom_type), 3),
+            (false, false) => (geom_type.to_string(), 2),
+        };
+        let srid = srid_from_projection(&sf.projection);
+        let fields = sf.attributes.get_fields().clone();
+        let has_field = |name: &str| fields.iter().any(|f| f.name.eq_ignore_ascii_case(name));
+        let geom_column = if has_field("geom") { "geometry" } else { "geom" };
+        let qualified_name = format!("{}.{}", quote_ident(&schema), quote_ident(&table));
+
+        let mut client = connect()?;
+        let mut tx = client.transaction().map_err(db_error)?;
+        let exists = !tx
+            .query(
+                "SELECT 1 FROM information_schema.tables WHERE table_schema = $1 AND table_name = $2",
+                &[&schema, &table],
+            )
+            .map_err(db_error)?
+            .is_empty();
+        if exists && get_configs()?.no_overwrite {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("The PostGIS table {}.{} already exists and the no_overwrite option is set.", schema, table),
+            ));
+        }
+
+        let mut column_defs = vec![];
+        if !has_field("fid") {
+            column_defs.push("\"fid\" serial PRIMARY KEY".to_string());
+        }
+        let mut casts = vec![];
+        for field in &fields {
+            let sql_type = match field.field_type {
+                'N' | 'I' | 'O' if field.decimal_count == 0 => "integer".to_string(),
+                'N' | 'F' | 'I' | 'O' => "double precision".to_string(),
+                'D' => "date".to_string(),
+                'L' => "boolean".to_string(),
+                _ => format!("varchar({})", field.field_length.max(1)),
+            };
+            column_defs.push(format!("{} {}", quote_ident(&field.name), sql_type));
+            casts.push(sql_type);
+        }
+        tx.batch_execute(&format!(
+            "DROP TABLE IF EXISTS {0}; CREATE TABLE {0} ({1})",
+            qualified_name,
+            column_defs.join(", ")
+        ))
+        .map_err(db_error)?;
+        tx.execute(
+            "SELECT AddGeometryColumn($1::varchar, $2::varchar, $3::varchar, $4::integer, $5::varchar, $6::integer)",
+            &[&schema, &table, &geom_column, &srid, &geom_type, &dimension],
+        )
+        .map_err(db_error)?;
+
+        // each record uses a parameter for each field and one for its geometry, and a
+        // statement may have at most 65535 parameters
+        let params_per_record = fields.len() + 1;
+        let chunk_size = INSERT_CHUNK_SIZE.min(65535 / params_per_record);
+        let mut column_names: Vec<String> = fields.iter().map(|f| quote_ident(&f.name)).collect();
+        column_names.push(quote_ident(geom_column));
+        let num_records = sf.records.len();
+        let mut start = 0;
+        while start < num_records {
+            let end = (start + chunk_size).min(num_records);
+            let mut values: Vec<Option<String>> = vec![];
+            let mut geometries: Vec<Option<Vec<u8>>> = vec![];
+            let mut rows = vec![];
+            for i in start..end {
+                let rec = if i < sf.attributes.header.num_records as usize {
+                    sf.attributes.get_record(i)
+                } else {
+                    vec![FieldData::Null; fields.len()]
+                };
+                let mut placeholders = vec![];
+                for (j, cast) in casts.iter().enumerate() {
+                    values.push(match rec.get(j).unwrap_or(&FieldData::Null) {
+                        FieldData::Int(v) => Some(v.to_string()),
+                        FieldData::Real(v) => Some(v.to_string()),
+                        FieldData::Text(v) => Some(v.clone()),
+                        FieldData::Date(d) => {
+                            Some(format!("{:04}-{:02}-{:02}", d.year, d.month, d.day))
+                        }
+                        FieldData::Bool(v) => Some(v.to_string()),
+                        FieldData::Null => None,
+                    });
+                    placeholders.push(format!("${}::text::{}", values.len(), cast));
+                }
+                geometries.push(encode_wkb(&sf.records[i], has_z, has_m, WKB_ENTITY));
+                placeholders.push(format!(
+                    "ST_GeomFromWKB(${}::bytea, {})",
+                    (end - start) * fields.len() + geometries.len(),
+                    srid
+                ));
+                rows.push(format!("({})", placeholders.join(", ")));
+            }
+            let mut params: Vec<&(dyn ToSql + Sync)> = vec![];
+            for v in &values {
+                params.push(v);
+            }
+            for g in &geometries {
+                params.push(g);
+            }
+            let sql = format!(
+                "INSERT INTO {} ({}) VALUES {}",
+                qualified_name,
+                column_names.join(", "),
+                rows.join(", ")
+            );
+            tx.execute(sql.as_str(), &params).map_err(db_error)?;
+            start = end;
+        }
+
+        tx.batch_execute(&format!(
+            "CREATE INDEX ON {} USING GIST ({})",
+            qualified_name,
+            quote_ident(geom_column)
+        ))
+        .map_err(db_error)?;
+        tx.commit().map_err(db_error)
+    }
+}
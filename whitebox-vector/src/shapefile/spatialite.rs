@@ -0,0 +1,203 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Writing vector datasets as SpatiaLite tables in a new SQLite database, for outputs named
+with a '.sqlite', '.sqlite3', or '.db' extension, so that attribute-heavy results can be queried
+with SQL (e.g. from the sqlite3 shell, QGIS, or GDAL) rather than through a DBF table, with its
+10-character field names and 255-field limit.
+
+The database holds a single table, named after the file (e.g. 'reaches' for 'reaches.sqlite'),
+with an 'fid' INTEGER PRIMARY KEY (unless the Shapefile has an FID field), the attribute fields,
+and a 'geom' column of SpatiaLite geometry blobs (POINT, MULTIPOINT, MULTILINESTRING, or
+MULTIPOLYGON), along with the SpatiaLite geometry_columns and spatial_ref_sys metadata tables.
+Integer fields are written as INTEGER, numeric fields with decimals as REAL, dates as DATE text
+('YYYY-MM-DD'), logical fields as BOOLEAN (0 or 1), and all other fields as TEXT. The SRID is taken
+from the last EPSG authority code of the projection, or 0 if there is none. No spatial index is
+built; one can be added with SpatiaLite's CreateSpatialIndex function.
+*/
+
+use super::attributes::*;
+use super::geometry::ShapefileGeometry;
+use super::wkb::*;
+use super::Shapefile;
+use std::io::{Error, ErrorKind};
+use whitebox_common::utils::{SqlValue, SqliteWriter};
+
+/// The byte that begins each geometry nested within a SpatiaLite multi-geometry.
+const SPATIALITE_ENTITY: u8 = 0x69;
+/// The byte that ends the minimum bounding rectangle that precedes a SpatiaLite geometry.
+const SPATIALITE_MBR_END: u8 = 0x7C;
+const SPATIALITE_END: u8 = 0xFE;
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Encodes a record as a SpatiaLite geometry blob, or returns None for a null record. The blob
+/// holds the SRID and bounding rectangle of the geometry followed by its WKB, with the byte
+/// order of each nested geometry replaced by an entity marker.
+fn encode_geometry(
+    sfg: &ShapefileGeometry,
+    has_z: bool,
+    has_m: bool,
+    srid: i32,
+) -> Option<Vec<u8>> {
+    let wkb = encode_wkb(sfg, has_z, has_m, SPATIALITE_ENTITY)?;
+    let n = sfg.num_points as usize;
+    let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+    let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for p in &sfg.points[..n] {
+        min_x = min_x.min(p.x);
+        min_y = min_y.min(p.y);
+        max_x = max_x.max(p.x);
+        max_y = max_y.max(p.y);
+    }
+    let mut blob = Vec::with_capacity(wkb.len() + 39);
+    blob.push(0u8);
+    blob.push(1u8); // little-endian
+    blob.extend_from_slice(&srid.to_le_bytes());
+    for v in [min_x, min_y, max_x, max_y] {
+        blob.extend_from_slice(&v.to_le_bytes());
+    }
+    // the outer geometry's type follows the rectangle, in place of an entity marker
+    blob.push(SPATIALITE_MBR_END);
+    blob.extend_from_slice(&wkb[1..]);
+    blob.push(SPATIALITE_END);
+    Some(blob)
+}
+
+/// Writes a Shapefile as a SpatiaLite table in a new database, named by its file name.
+pub(super) fn write_database(sf: &mut Shapefile) -> Result<(), Error> {
+    let table = sf.get_short_filename();
+    let shape_type = sf.header.shape_type;
+    let (geom_type, type_code) = geometry_type(shape_type).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            "Shapefiles of the Null shape type cannot be written to SpatiaLite.",
+        )
+    })?;
+    let (has_z, has_m) = output_dimensions(sf);
+    let (type_offset, dimension) = match (has_z, has_m) {
+        (true, true) => (3000, 4),
+        (true, false) => (1000, 3),
+        (false, true) => (2000, 3),
+        (false, false) => (0, 2),
+    };
+    let srid = srid_from_projection(&sf.projection);
+    let fields = sf.attributes.get_fields().clone();
+    let has_field = |name: &str| fields.iter().any(|f| f.name.eq_ignore_ascii_case(name));
+    let has_fid = has_field("fid");
+    let geom_column = if has_field("geom") {
+        "geometry"
+    } else {
+        "geom"
+    };
+
+    let mut column_defs = vec![];
+    if !has_fid {
+        column_defs.push("\"fid\" INTEGER PRIMARY KEY".to_string());
+    }
+    for field in &fields {
+        let sql_type = match field.field_type {
+            'N' | 'I' | 'O' if field.decimal_count == 0 => "INTEGER",
+            'N' | 'F' | 'I' | 'O' => "REAL",
+            'D' => "DATE",
+            'L' => "BOOLEAN",
+            _ => "TEXT",
+        };
+        column_defs.push(format!("{} {}", quote_ident(&field.name), sql_type));
+    }
+    column_defs.push(format!("{} {}", quote_ident(geom_column), geom_type));
+
+    let mut db = SqliteWriter::create(&sf.file_name)?;
+    let mut ref_systems = vec![
+        vec![
+            SqlValue::Integer(-1),
+            SqlValue::Text("NONE".to_string()),
+            SqlValue::Integer(-1),
+            SqlValue::Text("Undefined - Cartesian".to_string()),
+            SqlValue::Text(String::new()),
+            SqlValue::Text("Undefined".to_string()),
+        ],
+        vec![
+            SqlValue::Integer(0),
+            SqlValue::Text("NONE".to_string()),
+            SqlValue::Integer(0),
+            SqlValue::Text("Undefined - Geographic Long/Lat".to_string()),
+            SqlValue::Text(String::new()),
+            SqlValue::Text("Undefined".to_string()),
+        ],
+    ];
+    if srid > 0 {
+        ref_systems.push(vec![
+            SqlValue::Integer(srid as i64),
+            SqlValue::Text("epsg".to_string()),
+            SqlValue::Integer(srid as i64),
+            SqlValue::Text(format!("EPSG:{}", srid)),
+            SqlValue::Text(String::new()),
+            SqlValue::Text(sf.projection.clone()),
+        ]);
+    }
+    db.write_table(
+        "spatial_ref_sys",
+        "CREATE TABLE spatial_ref_sys (srid INTEGER NOT NULL, auth_name TEXT NOT NULL, auth_srid INTEGER NOT NULL, ref_sys_name TEXT NOT NULL DEFAULT 'Unknown', proj4text TEXT NOT NULL, srtext TEXT NOT NULL DEFAULT 'Undefined')",
+        ref_systems,
+    )?;
+    db.write_table(
+        "geometry_columns",
+        "CREATE TABLE geometry_columns (f_table_name TEXT NOT NULL, f_geometry_column TEXT NOT NULL, geometry_type INTEGER NOT NULL, coord_dimension INTEGER NOT NULL, srid INTEGER NOT NULL, spatial_index_enabled INTEGER NOT NULL)",
+        vec![vec![
+            SqlValue::Text(table.to_lowercase()),
+            SqlValue::Text(geom_column.to_string()),
+            SqlValue::Integer((type_code + type_offset) as i64),
+            SqlValue::Integer(dimension),
+            SqlValue::Integer(srid as i64),
+            SqlValue::Integer(0),
+        ]],
+    )?;
+
+    let num_attribute_records = sf.attributes.header.num_records as usize;
+    let rows = sf.records.iter().enumerate().map(|(i, sfg)| {
+        let rec = if i < num_attribute_records {
+            sf.attributes.get_record(i)
+        } else {
+            vec![FieldData::Null; fields.len()]
+        };
+        let mut row = Vec::with_capacity(fields.len() + 2);
+        if !has_fid {
+            // the rowid alias
+            row.push(SqlValue::Null);
+        }
+        for j in 0..fields.len() {
+            row.push(match rec.get(j).unwrap_or(&FieldData::Null) {
+                FieldData::Int(v) => SqlValue::Integer(*v as i64),
+                FieldData::Real(v) => SqlValue::Real(*v),
+                FieldData::Text(v) => SqlValue::Text(v.clone()),
+                FieldData::Date(d) => {
+                    SqlValue::Text(format!("{:04}-{:02}-{:02}", d.year, d.month, d.day))
+                }
+                FieldData::Bool(v) => SqlValue::Integer(*v as i64),
+                FieldData::Null => SqlValue::Null,
+            });
+        }
+        row.push(match encode_geometry(sfg, has_z, has_m, srid) {
+            Some(blob) => SqlValue::Blob(blob),
+            None => SqlValue::Null,
+        });
+        row
+    });
+    db.write_table(
+        &table,
+        &format!(
+            "CREATE TABLE {} ({})",
+            quote_ident(&table),
+            column_defs.join(", ")
+        ),
+        rows,
+    )?;
+    db.finish()
+}
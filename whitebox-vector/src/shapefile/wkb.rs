@@ -0,0 +1,186 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Encoding Shapefile records as well-known binary (WKB), shared by the PostGIS and
+SpatiaLite drivers. Points are written as POINTs, multi-points as MULTIPOINTs, polylines as
+MULTILINESTRINGs, and polygons as MULTIPOLYGONs, with each hole (counter-clockwise ring) placed in
+the polygon of the preceding exterior ring. Z and M dimensions use the ISO type codes (+1000 for Z,
++2000 for M, +3000 for ZM).
+*/
+
+use super::geometry::*;
+use super::Shapefile;
+use whitebox_common::algorithms::is_clockwise_order;
+
+// WKB geometry type codes
+pub(super) const WKB_POINT: u32 = 1;
+pub(super) const WKB_LINESTRING: u32 = 2;
+pub(super) const WKB_POLYGON: u32 = 3;
+pub(super) const WKB_MULTIPOINT: u32 = 4;
+pub(super) const WKB_MULTILINESTRING: u32 = 5;
+pub(super) const WKB_MULTIPOLYGON: u32 = 6;
+
+/// Returns the name and WKB type code of the geometry type with which the records of a
+/// Shapefile are written, or None for the Null shape type.
+pub(super) fn geometry_type(shape_type: ShapeType) -> Option<(&'static str, u32)> {
+    match shape_type.base_shape_type() {
+        ShapeType::Point => Some(("POINT", WKB_POINT)),
+        ShapeType::MultiPoint => Some(("MULTIPOINT", WKB_MULTIPOINT)),
+        ShapeType::PolyLine => Some(("MULTILINESTRING", WKB_MULTILINESTRING)),
+        ShapeType::Polygon => Some(("MULTIPOLYGON", WKB_MULTIPOLYGON)),
+        _ => None,
+    }
+}
+
+/// Returns whether the geometries of a Shapefile are written with z values and measures;
+/// measures are kept with z values unless none of the records has any.
+pub(super) fn output_dimensions(sf: &Shapefile) -> (bool, bool) {
+    match sf.header.shape_type.dimension() {
+        ShapeTypeDimension::Z => (true, sf.records.iter().any(|r| r.has_m_data())),
+        ShapeTypeDimension::Measure => (false, true),
+        ShapeTypeDimension::XY => (false, false),
+    }
+}
+
+/// Returns the EPSG code of the last authority of a WKT projection, or 0 if it has none.
+pub(super) fn srid_from_projection(projection: &str) -> i32 {
+    let upper = projection.to_uppercase();
+    let tag = "AUTHORITY[\"EPSG\",";
+    match upper.rfind(tag) {
+        Some(i) => upper[i + tag.len()..]
+            .trim_start_matches(|c: char| c == '"' || c.is_whitespace())
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect::<String>()
+            .parse::<i32>()
+            .unwrap_or(0),
+        None => 0,
+    }
+}
+
+struct WkbWriter {
+    data: Vec<u8>,
+    entity_marker: u8,
+    type_offset: u32,
+    has_z: bool,
+    has_m: bool,
+}
+
+impl WkbWriter {
+    fn begin(&mut self, wkb_type: u32) {
+        self.data.push(self.entity_marker);
+        self.data
+            .extend_from_slice(&(wkb_type + self.type_offset).to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_point(&mut self, sfg: &ShapefileGeometry, i: usize) {
+        self.data.extend_from_slice(&sfg.points[i].x.to_le_bytes());
+        self.data.extend_from_slice(&sfg.points[i].y.to_le_bytes());
+        if self.has_z {
+            let z = sfg.z_array.get(i).copied().unwrap_or(0f64);
+            self.data.extend_from_slice(&z.to_le_bytes());
+        }
+        if self.has_m {
+            let m = sfg.m_array.get(i).copied().unwrap_or(0f64);
+            self.data.extend_from_slice(&m.to_le_bytes());
+        }
+    }
+
+    fn write_ring(&mut self, sfg: &ShapefileGeometry, part: usize) {
+        let (start, end) = part_range(sfg, part);
+        self.write_u32((end - start) as u32);
+        for i in start..end {
+            self.write_point(sfg, i);
+        }
+    }
+}
+
+/// Returns the range of the points of a part of a record.
+fn part_range(sfg: &ShapefileGeometry, part: usize) -> (usize, usize) {
+    let start = sfg.parts[part] as usize;
+    let end = if part + 1 < sfg.num_parts as usize {
+        sfg.parts[part + 1] as usize
+    } else {
+        sfg.num_points as usize
+    };
+    (start, end)
+}
+
+/// Encodes a record as little-endian WKB, or returns None for a null record. Each geometry,
+/// including those nested within a multi-geometry, begins with `entity_marker` and its type
+/// code; for standard WKB, this is the byte order, 1 (little-endian).
+pub(super) fn encode_wkb(
+    sfg: &ShapefileGeometry,
+    has_z: bool,
+    has_m: bool,
+    entity_marker: u8,
+) -> Option<Vec<u8>> {
+    if sfg.shape_type == ShapeType::Null || sfg.num_points == 0 {
+        return None;
+    }
+    let mut w = WkbWriter {
+        data: vec![],
+        entity_marker,
+        type_offset: match (has_z, has_m) {
+            (true, true) => 3000,
+            (true, false) => 1000,
+            (false, true) => 2000,
+            (false, false) => 0,
+        },
+        has_z,
+        has_m,
+    };
+    match sfg.shape_type.base_shape_type() {
+        ShapeType::Point => {
+            w.begin(WKB_POINT);
+            w.write_point(sfg, 0);
+        }
+        ShapeType::MultiPoint => {
+            w.begin(WKB_MULTIPOINT);
+            w.write_u32(sfg.num_points as u32);
+            for i in 0..sfg.num_points as usize {
+                w.begin(WKB_POINT);
+                w.write_point(sfg, i);
+            }
+        }
+        ShapeType::PolyLine => {
+            w.begin(WKB_MULTILINESTRING);
+            w.write_u32(sfg.num_parts as u32);
+            for part in 0..sfg.num_parts as usize {
+                w.begin(WKB_LINESTRING);
+                w.write_ring(sfg, part);
+            }
+        }
+        _ => {
+            // each hole, i.e. counter-clockwise ring, belongs to the polygon of the
+            // preceding exterior ring
+            let mut polygons: Vec<Vec<usize>> = vec![];
+            for part in 0..sfg.num_parts as usize {
+                let (start, end) = part_range(sfg, part);
+                let is_hole = !is_clockwise_order(&sfg.points[start..end]);
+                match polygons.last_mut() {
+                    Some(rings) if is_hole => rings.push(part),
+                    _ => polygons.push(vec![part]),
+                }
+            }
+            w.begin(WKB_MULTIPOLYGON);
+            w.write_u32(polygons.len() as u32);
+            for rings in &polygons {
+                w.begin(WKB_POLYGON);
+                w.write_u32(rings.len() as u32);
+                for &part in rings {
+                    w.write_ring(sfg, part);
+                }
+            }
+        }
+    }
+    Some(w.data)
+}
@@ -0,0 +1,133 @@
+/*
+This file is part of the WhiteboxTools geospatial analysis library.
+Authors: Dr. John Lindsay
+Created: 16/10/2026
+Last Modified: 16/10/2026
+License: MIT
+
+NOTES: Vectors are read from and written to files by default. A `VectorProvider` supplies them
+from somewhere else instead, in the same way as the `RasterProvider` of the whitebox_raster crate
+does for rasters: a provider registered for a URL scheme (e.g. `app`) handles every vector whose
+name begins with that scheme (e.g. `app://streams.shp`), and tools read and write such vectors
+like any other. This is how vectors are supplied to the wasm32 build, which has no file system.
+
+Providers exchange whole `Shapefile`s, i.e. their geometries, attributes and projection.
+*/
+
+use super::Shapefile;
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind};
+use std::sync::{Arc, Mutex};
+use whitebox_common::utils::is_url;
+
+/// A source and destination of vectors other than files.
+pub trait VectorProvider: Send + Sync {
+    /// Reads the vector named `file_name`.
+    fn read(&self, file_name: &str) -> Result<Shapefile, Error>;
+
+    /// Writes a vector, replacing any existing vector of the same name.
+    fn write(&self, file_name: &str, vector: &Shapefile) -> Result<(), Error>;
+
+    /// Returns true if a vector named `file_name` exists.
+    fn exists(&self, file_name: &str) -> bool;
+}
+
+/// Holds vectors in memory. An application registers it for a scheme with
+/// `register_vector_provider`, keeping a clone of the `Arc` to add its inputs and take the
+/// outputs of tools.
+#[derive(Default)]
+pub struct MemoryVectorProvider {
+    vectors: Mutex<HashMap<String, Shapefile>>,
+}
+
+impl VectorProvider for MemoryVectorProvider {
+    fn read(&self, file_name: &str) -> Result<Shapefile, Error> {
+        let vectors = self
+            .vectors
+            .lock()
+            .map_err(|_| Error::new(ErrorKind::Other, "The in-memory vectors are unavailable."))?;
+        vectors.get(file_name.trim()).cloned().ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("The in-memory vector {} does not exist.", file_name.trim()),
+            )
+        })
+    }
+
+    fn write(&self, file_name: &str, vector: &Shapefile) -> Result<(), Error> {
+        let mut vectors = self
+            .vectors
+            .lock()
+            .map_err(|_| Error::new(ErrorKind::Other, "The in-memory vectors are unavailable."))?;
+        vectors.insert(file_name.trim().to_string(), vector.clone());
+        Ok(())
+    }
+
+    fn exists(&self, file_name: &str) -> bool {
+        self.vectors
+            .lock()
+            .map(|v| v.contains_key(file_name.trim()))
+            .unwrap_or(false)
+    }
+}
+
+static PROVIDERS: Mutex<Option<HashMap<String, Arc<dyn VectorProvider>>>> = Mutex::new(None);
+
+/// Registers `provider` for the vectors named with the URL scheme `scheme` (e.g. `app` for
+/// `app://streams.shp`), replacing any provider already registered for it. Schemes are not case
+/// sensitive, and a registered provider takes precedence over the built-in reading of URLs.
+pub fn register_vector_provider(scheme: &str, provider: Arc<dyn VectorProvider>) {
+    if let Ok(mut providers) = PROVIDERS.lock() {
+        providers
+            .get_or_insert_with(HashMap::new)
+            .insert(scheme.trim().to_lowercase(), provider);
+    }
+}
+
+/// Removes the vector provider registered for `scheme`, if any.
+pub fn unregister_vector_provider(scheme: &str) {
+    if let Ok(mut providers) = PROVIDERS.lock() {
+        if let Some(p) = providers.as_mut() {
+            p.remove(&scheme.trim().to_lowercase());
+        }
+    }
+}
+
+/// Returns the provider of the vector named `file_name`, or `None` if it is a file.
+pub(crate) fn vector_provider(file_name: &str) -> Option<Arc<dyn VectorProvider>> {
+    if !is_url(file_name) {
+        return None;
+    }
+    let file_name = file_name.trim();
+    let scheme = file_name[..file_name.find("://")?].to_lowercase();
+    let providers = PROVIDERS.lock().ok()?;
+    providers.as_ref()?.get(&scheme).cloned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ShapeType;
+
+    #[test]
+    fn test_vector_provider() {
+        let provider = Arc::new(MemoryVectorProvider::default());
+        register_vector_provider("VecTest", provider.clone());
+        let mut output = Shapefile::new("vectest://points", ShapeType::Point).unwrap();
+        assert_eq!(output.file_name, "vectest://points.shp");
+        output.add_point_record(1.0, 2.0);
+        output.add_point_record(3.0, 4.0);
+        output.projection = "EPSG:26917".to_string();
+        output.write().unwrap();
+        assert!(provider.exists("vectest://points.shp"));
+
+        let input = Shapefile::read("vectest://points.shp").unwrap();
+        assert_eq!(input.file_mode, "r");
+        assert_eq!(input.num_records, 2);
+        assert_eq!(input.get_record(1).points[0].y, 4.0);
+        assert_eq!(input.projection, "EPSG:26917");
+
+        unregister_vector_provider("vectest");
+        assert!(Shapefile::read("vectest://points.shp").is_err());
+    }
+}
@@ -71,6 +71,21 @@ class WhiteboxTools(object):
         self.verbose = True
         self.__compress_rasters = False
         self.__max_procs = -1
+        self.__out_dtype = "auto"
+        self.__out_scale = 1.0
+        self.__out_offset = 0.0
+        self.__compute = "cpu"
+        self.__no_overwrite = False
+        self.__cog = False
+        self.__geotiff_compression = "deflate"
+        self.__geotiff_predictor = False
+        self.__run_manifest = ""
+        self.__whitebox_raster_compression = "v1"
+        self.__postgis_connection = ""
+        self.__netcdf_band = 1
+        self.__netcdf_time = ""
+        self.__zarr_version = 2
+        self.__progress_callback = None
 
         if os.path.isfile('settings.json'):
             # read the settings.json file if it exists
@@ -83,6 +98,17 @@ class WhiteboxTools(object):
             self.verbose = str(settings['verbose_mode'])
             self.__compress_rasters = settings['compress_rasters']
             self.__max_procs = settings['max_procs']
+            self.__out_dtype = settings.get('out_dtype', "auto")
+            self.__out_scale = settings.get('out_scale', 1.0)
+            self.__out_offset = settings.get('out_offset', 0.0)
+            self.__compute = settings.get('compute', "cpu")
+            self.__no_overwrite = settings.get('no_overwrite', False)
+            self.__cog = settings.get('cog', False)
+            self.__geotiff_compression = settings.get('geotiff_compression', "deflate")
+            self.__geotiff_predictor = settings.get('geotiff_predictor', False)
+            self.__run_manifest = settings.get('run_manifest', "")
+            self.__whitebox_raster_compression = settings.get('whitebox_raster_compression', "v1")
+            self.__postgis_connection = settings.get('postgis_connection', "")
 
 
         self.cancel_op = False
@@ -274,6 +300,128 @@ class WhiteboxTools(object):
     
     def get_max_procs(self):
         return self.__max_procs
+
+    def set_out_dtype(self, dtype="auto", scale=1.0, offset=0.0):
+        ''' 
+        Sets the data type of output rasters used by subsequent tool runs. Options include 'auto' (categorical
+        integer outputs are narrowed to the smallest suitable type), 'native' (the type chosen by each tool),
+        'u8', 'i16', 'u16', 'i32', 'f32', and 'f64'. Values are stored as (value - offset) / scale.
+        '''
+        self.__out_dtype = dtype
+        self.__out_scale = scale
+        self.__out_offset = offset
+
+    def get_out_dtype(self):
+        return self.__out_dtype
+
+    def set_compute(self, device="cpu"):
+        ''' 
+        Sets the device, 'cpu' or 'gpu', used by subsequent runs of tools that support GPU acceleration.
+        Tools fall back to the CPU when no GPU is available.
+        '''
+        self.__compute = device
+
+    def get_compute(self):
+        return self.__compute
+
+    def set_no_overwrite(self, val=True):
+        ''' 
+        Sets whether subsequent tool runs refuse to replace existing output files.
+        '''
+        self.__no_overwrite = val
+
+    def get_no_overwrite(self):
+        return self.__no_overwrite
+
+    def set_cog(self, val=True):
+        ''' 
+        Sets whether subsequent tool runs write output GeoTIFFs as Cloud-Optimized GeoTIFFs.
+        '''
+        self.__cog = val
+
+    def get_cog(self):
+        return self.__cog
+
+    def set_geotiff_compression(self, val="deflate"):
+        ''' 
+        Sets the compression of the tiles of output GeoTIFFs in subsequent tool runs, one of 'none',
+        'lzw', 'deflate', or 'zstd'. Output GeoTIFFs are only compressed when compress_rasters is True.
+        '''
+        self.__geotiff_compression = val
+
+    def get_geotiff_compression(self):
+        return self.__geotiff_compression
+
+    def set_geotiff_predictor(self, val=True):
+        ''' 
+        Sets whether subsequent tool runs apply a predictor ahead of the compression of output GeoTIFFs.
+        '''
+        self.__geotiff_predictor = val
+
+    def get_geotiff_predictor(self):
+        return self.__geotiff_predictor
+
+    def set_run_manifest(self, file_name=""):
+        ''' 
+        Sets a JSON Lines file to which a record of each subsequent tool run is appended.
+        An empty string disables the run manifest.
+        '''
+        self.__run_manifest = file_name
+
+    def get_run_manifest(self):
+        return self.__run_manifest
+
+    def set_progress_callback(self, callback=None):
+        ''' 
+        Sets a function that receives the progress of subsequent tool runs, as callback(label, progress),
+        e.g. callback("Progress", 45), instead of the progress messages being sent to the run_tool
+        callback. None restores the default behaviour.
+        '''
+        self.__progress_callback = callback
+
+    def get_progress_callback(self):
+        return self.__progress_callback
+
+    def set_whitebox_raster_compression(self, val="v1"):
+        ''' 
+        Sets the layout of Whitebox (.dep) output rasters: 'v1' for the original format, or 'none',
+        'lz4', 'deflate', or 'zstd' for the tiled v2 format using that tile compression.
+        '''
+        self.__whitebox_raster_compression = val
+
+    def get_whitebox_raster_compression(self):
+        return self.__whitebox_raster_compression
+
+    def set_postgis_connection(self, conn_str=""):
+        ''' 
+        Sets the connection string of the database holding vector datasets named 'postgis:schema.table',
+        e.g. "host=localhost dbname=gis user=postgres". Requires a build with the postgis feature.
+        '''
+        self.__postgis_connection = conn_str
+
+    def get_postgis_connection(self):
+        return self.__postgis_connection
+
+    def set_netcdf_selection(self, band=1, time=""):
+        ''' 
+        Sets the band (1-based) and time step, given as a date (e.g. "2020-07-01") or time coordinate value,
+        read from multi-dimensional NetCDF inputs by subsequent tool runs. When a time is given, the band
+        selects among the other leading (e.g. depth) dimensions of the NetCDF variable.
+        '''
+        self.__netcdf_band = band
+        self.__netcdf_time = time
+
+    def get_netcdf_selection(self):
+        return (self.__netcdf_band, self.__netcdf_time)
+
+    def set_zarr_version(self, version=2):
+        ''' 
+        Sets the Zarr format version (2 or 3) of the Zarr (.zarr) stores written by subsequent tool runs.
+        '''
+        self.__zarr_version = version
+
+    def get_zarr_version(self):
+        return self.__zarr_version
     
     def run_tool(self, tool_name, args, callback=None):
         ''' 
@@ -310,6 +458,45 @@ class WhiteboxTools(object):
             else:
                 args2.append("--compress_rasters=False")
 
+            args2.append("--whitebox_raster_compression={}".format(self.__whitebox_raster_compression))
+
+            if self.__out_dtype != "auto" or self.__out_scale != 1.0 or self.__out_offset != 0.0:
+                args2.append("--out_dtype={}".format(self.__out_dtype))
+                args2.append("--out_dtype_scale={}".format(self.__out_scale))
+                args2.append("--out_dtype_offset={}".format(self.__out_offset))
+
+            if self.__compute != "cpu":
+                args2.append("--compute={}".format(self.__compute))
+
+            if self.__no_overwrite:
+                args2.append("--no_overwrite")
+
+            if self.__cog:
+                args2.append("--cog")
+
+            args2.append("--geotiff_compression={}".format(self.__geotiff_compression))
+
+            if self.__geotiff_predictor:
+                args2.append("--geotiff_predictor")
+
+            if self.__run_manifest.strip() != "":
+                args2.append("--manifest=\"{}\"".format(self.__run_manifest))
+
+            if self.__postgis_connection.strip() != "":
+                args2.append("--postgis_connection={}".format(self.__postgis_connection))
+
+            if self.__netcdf_band != 1:
+                args2.append("--band={}".format(self.__netcdf_band))
+
+            if str(self.__netcdf_time).strip() != "":
+                args2.append("--time=\"{}\"".format(self.__netcdf_time))
+
+            if self.__zarr_version != 2:
+                args2.append("--zarr_version={}".format(self.__zarr_version))
+
+            if self.__progress_callback is not None:
+                args2.append("--progress=json")
+
             if self.verbose:
                 cl = " ".join(args2)
                 callback(cl.strip() + "\n")
@@ -332,6 +519,13 @@ class WhiteboxTools(object):
                 sys.stdout.flush()
                 if line != '':
                     if not self.cancel_op:
+                        if self.__progress_callback is not None and line.startswith('{"label"'):
+                            try:
+                                event = json.loads(line)
+                                self.__progress_callback(event['label'], event['progress'])
+                                continue
+                            except (ValueError, KeyError):
+                                pass
                         if self.verbose:
                             callback(line.strip())
                     else:
@@ -370,6 +564,64 @@ class WhiteboxTools(object):
         except (OSError, ValueError, CalledProcessError) as err:
             return err
 
+    def benchmark(self, tool_name, args, repetitions=3):
+        ''' 
+        Runs a tool repeatedly at increasing thread counts and returns the JSON performance
+        report, which gives the wall, I/O, and compute times and peak memory use of the runs.
+        '''
+        try:
+            os.chdir(self.exe_path)
+            args2 = []
+            args2.append("." + os.path.sep + self.exe_name)
+            args2.append("--run=\"{}\"".format(to_camelcase(tool_name)))
+            if self.work_dir.strip() != "":
+                args2.append("--wd=\"{}\"".format(self.work_dir))
+            for arg in args:
+                args2.append(arg)
+            args2.append("--benchmark={}".format(repetitions))
+
+            proc = Popen(args2, shell=False, stdout=PIPE,
+                         stderr=STDOUT, bufsize=1, universal_newlines=True)
+            ret = ""
+            while True:
+                line = proc.stdout.readline()
+                if line != '':
+                    ret += line
+                else:
+                    break
+
+            return ret
+        except (OSError, ValueError, CalledProcessError) as err:
+            return err
+
+    def self_test(self, name=None):
+        ''' 
+        Runs the built-in tests of tools on synthetic datasets and returns the test report.
+        A tool or test name may be used to select the tests that are run.
+        '''
+        try:
+            os.chdir(self.exe_path)
+            args = []
+            args.append("." + os.path.sep + self.exe_name)
+            if name is not None:
+                args.append("--selftest={}".format(name))
+            else:
+                args.append("--selftest")
+
+            proc = Popen(args, shell=False, stdout=PIPE,
+                         stderr=STDOUT, bufsize=1, universal_newlines=True)
+            ret = ""
+            while True:
+                line = proc.stdout.readline()
+                if line != '':
+                    ret += line
+                else:
+                    break
+
+            return ret
+        except (OSError, ValueError, CalledProcessError) as err:
+            return err
+
     def license(self, toolname=None):
         ''' 
         Retrieves the license information for WhiteboxTools.
@@ -468,6 +720,34 @@ class WhiteboxTools(object):
         except (OSError, ValueError, CalledProcessError) as err:
             return err
 
+    def list_presets(self, tool_name=''):
+        '''
+        Retrieves the parameter presets, as JSON, of a specific tool or of all tools. A preset is
+        applied to a run by including '--preset=name' in the args of run_tool.
+        '''
+        try:
+            os.chdir(self.exe_path)
+            args = []
+            args.append("." + os.path.sep + self.exe_name)
+            if len(tool_name) > 0:
+                args.append("--listpresets={}".format(to_camelcase(tool_name)))
+            else:
+                args.append("--listpresets")
+
+            proc = Popen(args, shell=False, stdout=PIPE,
+                         stderr=STDOUT, bufsize=1, universal_newlines=True)
+            ret = ""
+            while True:
+                line = proc.stdout.readline()
+                if line != '':
+                    ret += line
+                else:
+                    break
+
+            return ret
+        except (OSError, ValueError, CalledProcessError) as err:
+            return err
+
     def toolbox(self, tool_name=''):
         ''' 
         Retrieve the toolbox for a specific tool.
@@ -803,6 +1083,24 @@ Okay, that's it for now.
         if epsg is not None: args.append("--epsg='{}'".format(epsg))
         return self.run_tool('csv_points_to_vector', args, callback) # returns 1 if error
 
+    def export_stac_items(self, inputs, output, collection=None, description=None, callback=None):
+        """Writes STAC Items describing files, with their processing history, and a STAC Catalog or Collection.
+
+        Keyword arguments:
+
+        inputs -- Input files, e.g. raster and vector tool outputs. 
+        output -- Output STAC catalog (or collection) JSON file, e.g. catalog.json. 
+        collection -- Collection ID; if specified, a STAC Collection is written rather than a Catalog (optional). 
+        description -- Description of the catalog or collection (optional). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--inputs='{}'".format(inputs))
+        args.append("--output='{}'".format(output))
+        if collection is not None: args.append("--collection='{}'".format(collection))
+        if description is not None: args.append("--description='{}'".format(description))
+        return self.run_tool('export_stac_items', args, callback) # returns 1 if error
+
     def export_table_to_csv(self, i, output, headers=True, callback=None):
         """Exports an attribute table to a CSV text file.
 
@@ -835,6 +1133,24 @@ Okay, that's it for now.
         args.append("--dist={}".format(dist))
         return self.run_tool('fix_dangling_arcs', args, callback) # returns 1 if error
 
+    def hex_to_square_grid(self, i, output, cell_size=None, base=None, callback=None):
+        """Converts a hexagonal grid raster in the offset (odd-r) layout onto a square grid.
+
+        Keyword arguments:
+
+        i -- Input hexagonal grid raster file. 
+        output -- Output raster file. 
+        cell_size -- Output cell size; defaults to the hexagon width. Ignored if a base raster is specified. 
+        base -- Optional raster defining the output grid. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if cell_size is not None: args.append("--cell_size='{}'".format(cell_size))
+        if base is not None: args.append("--base='{}'".format(base))
+        return self.run_tool('hex_to_square_grid', args, callback) # returns 1 if error
+
     def join_tables(self, input1, pkey, input2, fkey, import_field, callback=None):
         """Merge a vector's attribute table with another table based on a common field.
 
@@ -855,6 +1171,22 @@ Okay, that's it for now.
         args.append("--import_field='{}'".format(import_field))
         return self.run_tool('join_tables', args, callback) # returns 1 if error
 
+    def lines_to_points(self, i, output, interval=None, callback=None):
+        """Converts vector polylines to points at vertices or regular intervals, with distance-along attributes.
+
+        Keyword arguments:
+
+        i -- Input vector polyline or polygon file. 
+        output -- Output vector points file. 
+        interval -- Distance between output points along lines; vertices are output if unspecified (optional). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if interval is not None: args.append("--interval='{}'".format(interval))
+        return self.run_tool('lines_to_points', args, callback) # returns 1 if error
+
     def lines_to_polygons(self, i, output, callback=None):
         """Converts vector polylines to polygons.
 
@@ -953,6 +1285,42 @@ Okay, that's it for now.
         if cell_size is not None: args.append("--cell_size='{}'".format(cell_size))
         return self.run_tool('new_raster_from_base', args, callback) # returns 1 if error
 
+    def pipeline(self, i, dry_run=False, callback=None):
+        """Runs a workflow of tools described in a JSON file, passing intermediate rasters between steps in memory.
+
+        Keyword arguments:
+
+        i -- Input JSON workflow file. 
+        dry_run -- Print the order of the steps without running them. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        if dry_run: args.append("--dry_run")
+        return self.run_tool('pipeline', args, callback) # returns 1 if error
+
+    def points_to_lines(self, i, output, group_field=None, order_field=None, max_gap=None, measure=False, callback=None):
+        """Builds vector polylines from ordered sequences of vector points.
+
+        Keyword arguments:
+
+        i -- Input vector points file. 
+        output -- Output vector polyline file. 
+        group_field -- Field used to group points into separate lines (optional). 
+        order_field -- Field used to order the points within lines (optional). 
+        max_gap -- Distance between consecutive points above which a line is broken (optional). 
+        measure -- Assign the distance along the line as the measure of each output vertex. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if group_field is not None: args.append("--group_field='{}'".format(group_field))
+        if order_field is not None: args.append("--order_field='{}'".format(order_field))
+        if max_gap is not None: args.append("--max_gap='{}'".format(max_gap))
+        if measure: args.append("--measure")
+        return self.run_tool('points_to_lines', args, callback) # returns 1 if error
+
     def polygons_to_lines(self, i, output, callback=None):
         """Converts vector polygons to polylines.
 
@@ -979,6 +1347,22 @@ Okay, that's it for now.
         args.append("--input='{}'".format(i))
         return self.run_tool('print_geo_tiff_tags', args, callback) # returns 1 if error
 
+    def raster_summary(self, i, output=None, histogram=False, callback=None):
+        """Reports the header information, CRS, data type, statistics, and file layout of a raster as JSON.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output JSON file; if unspecified, the summary is printed. 
+        histogram -- Include a 256-bin histogram of the raster values. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        if output is not None: args.append("--output='{}'".format(output))
+        if histogram: args.append("--histogram")
+        return self.run_tool('raster_summary', args, callback) # returns 1 if error
+
     def raster_to_vector_lines(self, i, output, callback=None):
         """Converts a raster lines features into a vector of the POLYLINE shapetype.
 
@@ -1021,6 +1405,30 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('raster_to_vector_polygons', args, callback) # returns 1 if error
 
+    def raster_to_xyz(self, i, output, delimiter="comma", header=False, include_nodata=False, step=1, decimals=None, callback=None):
+        """Exports the cells of a raster to a delimited XYZ/CSV text file.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output delimited text file. 
+        delimiter -- Field delimiter; options include 'comma', 'space', 'tab', and 'semicolon'. 
+        header -- Write a header line naming the fields. 
+        include_nodata -- Include nodata cells in the output. 
+        step -- Write every nth row and column. 
+        decimals -- Number of decimal places used for cell values; by default, full precision is used. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--delimiter={}".format(delimiter))
+        if header: args.append("--header")
+        if include_nodata: args.append("--include_nodata")
+        args.append("--step={}".format(step))
+        if decimals is not None: args.append("--decimals='{}'".format(decimals))
+        return self.run_tool('raster_to_xyz', args, callback) # returns 1 if error
+
     def reinitialize_attribute_table(self, i, callback=None):
         """Reinitializes a vector's attribute table deleting all fields but the feature ID (FID).
 
@@ -1079,6 +1487,94 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('single_part_to_multi_part', args, callback) # returns 1 if error
 
+    def split_vector_layer(self, i, field=None, grid=None, grid_field=None, outdir=None, prefix=None, callback=None):
+        """Splits a vector layer into separate files by the unique values of a field or by the tiles of a grid.
+
+        Keyword arguments:
+
+        i -- Input vector file. 
+        field -- Input field used to split the layer (optional). 
+        grid -- Input vector polygon grid used to split the layer (optional). 
+        grid_field -- Grid field used to name the output tiles (optional). 
+        outdir -- Output directory (optional; defaults to the working directory). 
+        prefix -- Output file name prefix (optional; defaults to the input file name). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        if field is not None: args.append("--field='{}'".format(field))
+        if grid is not None: args.append("--grid='{}'".format(grid))
+        if grid_field is not None: args.append("--grid_field='{}'".format(grid_field))
+        if outdir is not None: args.append("--outdir='{}'".format(outdir))
+        if prefix is not None: args.append("--prefix='{}'".format(prefix))
+        return self.run_tool('split_vector_layer', args, callback) # returns 1 if error
+
+    def square_to_hex_grid(self, i, output, width=None, method="nn", callback=None):
+        """Resamples a square-grid raster onto a hexagonal grid stored in an offset (odd-r) layout.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output hexagonal grid raster file. 
+        width -- Distance between the centres of neighbouring hexagons; defaults to the input cell size. 
+        method -- Resampling method; 'nn' (nearest neighbour) or 'mean'. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if width is not None: args.append("--width='{}'".format(width))
+        args.append("--method={}".format(method))
+        return self.run_tool('square_to_hex_grid', args, callback) # returns 1 if error
+
+    def stac_search(self, url, output, collections=None, bbox=None, datetime=None, assets=None, max_items=10, outdir=None, list_only=False, callback=None):
+        """Searches a STAC API for items by collection, bounding box and date, and downloads their assets.
+
+        Keyword arguments:
+
+        url -- Root URL of the STAC API, e.g. https://earth-search.aws.element84.com/v1. 
+        collections -- Comma-separated list of collection IDs to search (optional). 
+        bbox -- Bounding box as west,south,east,north in geographic coordinates, or a raster or vector file whose extent is used (optional). 
+        datetime -- RFC 3339 date-time or interval, e.g. 2023-06-01/2023-08-31 (optional). 
+        assets -- Comma-separated list of the keys of the assets to download (optional; defaults to the data assets). 
+        max_items -- Maximum number of items to retrieve. 
+        outdir -- Output directory for the downloaded assets (optional; defaults to the working directory). 
+        output -- Output STAC ItemCollection (GeoJSON) file. 
+        list_only -- Write the matching items without downloading their assets. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--url='{}'".format(url))
+        if collections is not None: args.append("--collections='{}'".format(collections))
+        if bbox is not None: args.append("--bbox='{}'".format(bbox))
+        if datetime is not None: args.append("--datetime='{}'".format(datetime))
+        if assets is not None: args.append("--assets='{}'".format(assets))
+        args.append("--max_items={}".format(max_items))
+        if outdir is not None: args.append("--outdir='{}'".format(outdir))
+        args.append("--output='{}'".format(output))
+        if list_only: args.append("--list_only")
+        return self.run_tool('stac_search', args, callback) # returns 1 if error
+
+    def translate_nodata(self, i, output, new_nodata=None, values=None, ranges=None, callback=None):
+        """Rewrites the nodata value of a raster and masks specified values and value ranges as nodata.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output raster file. 
+        new_nodata -- New nodata value; by default, the input nodata value is retained. 
+        values -- Values to convert to nodata, separated by commas or semicolons. 
+        ranges -- Inclusive value ranges to convert to nodata, e.g. ':-1000;250:'. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if new_nodata is not None: args.append("--new_nodata={}".format(new_nodata))
+        if values is not None: args.append("--values='{}'".format(values))
+        if ranges is not None: args.append("--ranges='{}'".format(ranges))
+        return self.run_tool('translate_nodata', args, callback) # returns 1 if error
+
     def vector_lines_to_raster(self, i, output, field="FID", nodata=True, cell_size=None, base=None, callback=None):
         """Converts a vector containing polylines into a raster.
 
@@ -1147,11 +1643,43 @@ Okay, that's it for now.
         if base is not None: args.append("--base='{}'".format(base))
         return self.run_tool('vector_polygons_to_raster', args, callback) # returns 1 if error
 
+    def xyz_to_raster(self, i, output, xfield=0, yfield=1, zfield=2, method="nearest", radius=None, weight=2.0, cell_size=None, base=None, epsg=None, callback=None):
+        """Grids the points in a delimited XYZ/CSV text file to a raster.
+
+        Keyword arguments:
+
+        i -- Input delimited text file. 
+        output -- Output raster file. 
+        xfield -- X field number (e.g. 0 for first field). 
+        yfield -- Y field number (e.g. 1 for second field). 
+        zfield -- Z field number (e.g. 2 for third field). 
+        method -- Interpolation method; options include 'nearest', 'idw', 'mean', 'min', and 'max'. 
+        radius -- Search radius used by the nearest and idw methods; defaults to twice the cell size. 
+        weight -- IDW weight value. 
+        cell_size -- Optionally specified cell size of output raster. Not used when base raster is specified. 
+        base -- Optionally specified input base raster file. Not used when a cell size is specified. 
+        epsg -- EPSG projection (e.g. 2958). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--xfield={}".format(xfield))
+        args.append("--yfield={}".format(yfield))
+        args.append("--zfield={}".format(zfield))
+        args.append("--method={}".format(method))
+        if radius is not None: args.append("--radius='{}'".format(radius))
+        args.append("--weight={}".format(weight))
+        if cell_size is not None: args.append("--cell_size='{}'".format(cell_size))
+        if base is not None: args.append("--base='{}'".format(base))
+        if epsg is not None: args.append("--epsg='{}'".format(epsg))
+        return self.run_tool('xyz_to_raster', args, callback) # returns 1 if error
+
     ################
     # GIS Analysis #
     ################
 
-    def aggregate_raster(self, i, output, agg_factor=2, type="mean", callback=None):
+    def aggregate_raster(self, i, output, agg_factor=2, type="mean", max_nodata=1.0, callback=None):
         """Aggregates a raster to a lower resolution.
 
         Keyword arguments:
@@ -1160,6 +1688,7 @@ Okay, that's it for now.
         output -- Output raster file. 
         agg_factor -- Aggregation factor, in pixels. 
         type -- Statistic used to fill output pixels. 
+        max_nodata -- Maximum fraction (0-1) of NoData input cells for which an output value is calculated. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
@@ -1167,6 +1696,7 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         args.append("--agg_factor={}".format(agg_factor))
         args.append("--type={}".format(type))
+        args.append("--max_nodata={}".format(max_nodata))
         return self.run_tool('aggregate_raster', args, callback) # returns 1 if error
 
     def block_maximum_gridding(self, i, field, output, use_z=False, cell_size=None, base=None, callback=None):
@@ -1213,6 +1743,52 @@ Okay, that's it for now.
         if base is not None: args.append("--base='{}'".format(base))
         return self.run_tool('block_minimum_gridding', args, callback) # returns 1 if error
 
+    def block_statistics(self, i, output, block_size=10, block_x=None, block_y=None, type="mean", max_nodata=1.0, preserve_nodata=False, callback=None):
+        """Calculates statistics over non-overlapping blocks of cells, output at the original resolution.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output raster file. 
+        block_size -- Block size, in grid cells. 
+        block_x -- Optional block width, in columns; overrides the block size. 
+        block_y -- Optional block height, in rows; overrides the block size. 
+        type -- Statistic calculated for each block. 
+        max_nodata -- Maximum fraction (0-1) of NoData cells in a block for which a statistic is calculated. 
+        preserve_nodata -- Keep input NoData cells as NoData in the output?. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--block_size={}".format(block_size))
+        if block_x is not None: args.append("--block_x='{}'".format(block_x))
+        if block_y is not None: args.append("--block_y='{}'".format(block_y))
+        args.append("--type={}".format(type))
+        args.append("--max_nodata={}".format(max_nodata))
+        if preserve_nodata: args.append("--preserve_nodata")
+        return self.run_tool('block_statistics', args, callback) # returns 1 if error
+
+    def calibrate_routes(self, i, output, from_field=None, to_field=None, factor=1.0, callback=None):
+        """Assigns linear referencing measures to the vertices of vector polyline routes.
+
+        Keyword arguments:
+
+        i -- Input vector polyline routes file. 
+        output -- Output measured vector polyline file. 
+        from_field -- Numeric field containing the measure of the start of each route (optional). 
+        to_field -- Numeric field containing the measure of the end of each route (optional). 
+        factor -- Multiplier applied to distances when measures are derived from route length. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if from_field is not None: args.append("--from_field='{}'".format(from_field))
+        if to_field is not None: args.append("--to_field='{}'".format(to_field))
+        args.append("--factor={}".format(factor))
+        return self.run_tool('calibrate_routes', args, callback) # returns 1 if error
+
     def centroid(self, i, output, text_output=False, callback=None):
         """Calculates the centroid, or average location, of raster polygon objects.
 
@@ -1341,7 +1917,29 @@ Okay, that's it for now.
         args.append("--yorig={}".format(yorig))
         return self.run_tool('create_rectangular_vector_grid', args, callback) # returns 1 if error
 
-    def dissolve(self, i, output, field=None, snap=0.0, callback=None):
+    def disaggregate_raster(self, i, output, factor=2, base=None, weights=None, method="replicate", callback=None):
+        """Splits the cells of a coarse raster onto a finer grid, with optional dasymetric weights.
+
+        Keyword arguments:
+
+        i -- Input coarse-resolution raster file. 
+        output -- Output raster file. 
+        factor -- Number of output cells per input cell, in each direction; ignored if a base or weights raster is specified. 
+        base -- Optional raster defining the output grid. 
+        weights -- Optional dasymetric weights raster, defining the output grid. 
+        method -- Disaggregation method; 'replicate' for intensive and 'divide' for extensive quantities. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--factor={}".format(factor))
+        if base is not None: args.append("--base='{}'".format(base))
+        if weights is not None: args.append("--weights='{}'".format(weights))
+        args.append("--method={}".format(method))
+        return self.run_tool('disaggregate_raster', args, callback) # returns 1 if error
+
+    def dissolve(self, i, output, field=None, snap=0.0, aggregate=None, multi_part=False, callback=None):
         """Removes the interior, or shared, boundaries within a vector polygon coverage.
 
         Keyword arguments:
@@ -1350,6 +1948,8 @@ Okay, that's it for now.
         field -- Dissolve field attribute (optional). 
         output -- Output vector file. 
         snap -- Snap tolerance. 
+        aggregate -- List of FIELD:FUNCTION pairs, separated by semicolons, where FUNCTION is one of sum, mean, min, max, or concat (optional). 
+        multi_part -- Combine all dissolved regions sharing a field value into a single multi-part feature. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
@@ -1357,6 +1957,8 @@ Okay, that's it for now.
         if field is not None: args.append("--field='{}'".format(field))
         args.append("--output='{}'".format(output))
         args.append("--snap={}".format(snap))
+        if aggregate is not None: args.append("--aggregate='{}'".format(aggregate))
+        if multi_part: args.append("--multi_part")
         return self.run_tool('dissolve', args, callback) # returns 1 if error
 
     def eliminate_coincident_points(self, i, output, tolerance, callback=None):
@@ -1523,6 +2125,70 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('layer_footprint', args, callback) # returns 1 if error
 
+    def locate_route_events(self, routes, route_field, events, event_route_field, output, measure_field=None, from_field=None, to_field=None, offset_field=None, callback=None):
+        """Locates point and line events from a table along measured routes (dynamic segmentation).
+
+        Keyword arguments:
+
+        routes -- Input vector polyline routes file. 
+        route_field -- Field in the routes attribute table identifying each route. 
+        events -- Input events table file. 
+        event_route_field -- Name of the events table field containing route identifiers. 
+        measure_field -- Name of the events table field containing point event measures (optional). 
+        from_field -- Name of the events table field containing line event from-measures (optional). 
+        to_field -- Name of the events table field containing line event to-measures (optional). 
+        offset_field -- Name of the events table field containing lateral offsets of point events (optional). 
+        output -- Output vector file. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--routes='{}'".format(routes))
+        args.append("--route_field='{}'".format(route_field))
+        args.append("--events='{}'".format(events))
+        args.append("--event_route_field='{}'".format(event_route_field))
+        if measure_field is not None: args.append("--measure_field='{}'".format(measure_field))
+        if from_field is not None: args.append("--from_field='{}'".format(from_field))
+        if to_field is not None: args.append("--to_field='{}'".format(to_field))
+        if offset_field is not None: args.append("--offset_field='{}'".format(offset_field))
+        args.append("--output='{}'".format(output))
+        return self.run_tool('locate_route_events', args, callback) # returns 1 if error
+
+    def mean_center(self, i, output, weight_field=None, case_field=None, callback=None):
+        """Calculates the (weighted) mean center of a vector points layer, optionally by case.
+
+        Keyword arguments:
+
+        i -- Input vector points file. 
+        output -- Output vector points file. 
+        weight_field -- Numeric field used to weight the points (optional). 
+        case_field -- Field used to group the points into cases (optional). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if weight_field is not None: args.append("--weight_field='{}'".format(weight_field))
+        if case_field is not None: args.append("--case_field='{}'".format(case_field))
+        return self.run_tool('mean_center', args, callback) # returns 1 if error
+
+    def median_center(self, i, output, weight_field=None, case_field=None, callback=None):
+        """Calculates the (weighted) median center of a vector points layer, optionally by case.
+
+        Keyword arguments:
+
+        i -- Input vector points file. 
+        output -- Output vector points file. 
+        weight_field -- Numeric field used to weight the points (optional). 
+        case_field -- Field used to group the points into cases (optional). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if weight_field is not None: args.append("--weight_field='{}'".format(weight_field))
+        if case_field is not None: args.append("--case_field='{}'".format(case_field))
+        return self.run_tool('median_center', args, callback) # returns 1 if error
+
     def medoid(self, i, output, callback=None):
         """Calculates the medoid for a series of vector features contained in a shapefile.
 
@@ -1648,7 +2314,7 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         if cell_size is not None: args.append("--cell_size='{}'".format(cell_size))
         if base is not None: args.append("--base='{}'".format(base))
-        if max_dist is not None: args.append("--max_dist='{}'".format(max_dist))
+        if max_dist is not None: args.append("--max_dist={}".format(max_dist))
         return self.run_tool('nearest_neighbour_gridding', args, callback) # returns 1 if error
 
     def polygon_area(self, i, callback=None):
@@ -1861,6 +2527,28 @@ Okay, that's it for now.
         args.append("--filter={}".format(filter))
         return self.run_tool('smooth_vectors', args, callback) # returns 1 if error
 
+    def snap_vectors(self, i, output, reference=None, tolerance=1.0, vertices_only=False, insert_nodes=False, callback=None):
+        """Snaps the vertices and edges of a vector to those of a reference vector, or to itself, within a tolerance.
+
+        Keyword arguments:
+
+        i -- Input vector file. 
+        reference -- Input reference vector file. If unspecified, the input is snapped to itself. 
+        output -- Output vector file. 
+        tolerance -- Snap tolerance, in map units. 
+        vertices_only -- Only snap to reference vertices and not to reference edges. 
+        insert_nodes -- Insert vertices where input edges cross, or pass within the tolerance of, the reference features. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        if reference is not None: args.append("--reference='{}'".format(reference))
+        args.append("--output='{}'".format(output))
+        args.append("--tolerance={}".format(tolerance))
+        if vertices_only: args.append("--vertices_only")
+        if insert_nodes: args.append("--insert_nodes")
+        return self.run_tool('snap_vectors', args, callback) # returns 1 if error
+
     def split_vector_lines(self, i, output, length=None, callback=None):
         """This tool can be used to split a vector line coverage into even-lengthed segments.
 
@@ -1877,6 +2565,46 @@ Okay, that's it for now.
         if length is not None: args.append("--length='{}'".format(length))
         return self.run_tool('split_vector_lines', args, callback) # returns 1 if error
 
+    def standard_deviational_ellipse(self, i, output, weight_field=None, case_field=None, num_std=1.0, callback=None):
+        """Calculates the standard deviational ellipse of a vector points layer, optionally by case.
+
+        Keyword arguments:
+
+        i -- Input vector points file. 
+        output -- Output vector polygon file. 
+        weight_field -- Numeric field used to weight the points (optional). 
+        case_field -- Field used to group the points into cases (optional). 
+        num_std -- Number of standard deviations used to size the output polygons. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if weight_field is not None: args.append("--weight_field='{}'".format(weight_field))
+        if case_field is not None: args.append("--case_field='{}'".format(case_field))
+        args.append("--num_std={}".format(num_std))
+        return self.run_tool('standard_deviational_ellipse', args, callback) # returns 1 if error
+
+    def standard_distance(self, i, output, weight_field=None, case_field=None, num_std=1.0, callback=None):
+        """Calculates the standard distance of a vector points layer, output as circles, optionally by case.
+
+        Keyword arguments:
+
+        i -- Input vector points file. 
+        output -- Output vector polygon file. 
+        weight_field -- Numeric field used to weight the points (optional). 
+        case_field -- Field used to group the points into cases (optional). 
+        num_std -- Number of standard deviations used to size the output polygons. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if weight_field is not None: args.append("--weight_field='{}'".format(weight_field))
+        if case_field is not None: args.append("--case_field='{}'".format(case_field))
+        args.append("--num_std={}".format(num_std))
+        return self.run_tool('standard_distance', args, callback) # returns 1 if error
+
     def tin_gridding(self, i, output, field=None, use_z=False, resolution=None, base=None, max_triangle_edge_length=None, callback=None):
         """Creates a raster grid based on a triangular irregular network (TIN) fitted to vector points.
 
@@ -1971,7 +2699,7 @@ Okay, that's it for now.
         if gridcells: args.append("--gridcells")
         return self.run_tool('buffer_raster', args, callback) # returns 1 if error
 
-    def cost_allocation(self, source, backlink, output, callback=None):
+    def cost_allocation(self, source, backlink, output, align="none", callback=None):
         """Identifies the source cell to which each grid cell is connected by a least-cost pathway in a cost-distance analysis.
 
         Keyword arguments:
@@ -1979,15 +2707,17 @@ Okay, that's it for now.
         source -- Input source raster file. 
         backlink -- Input backlink raster file generated by the cost-distance tool. 
         output -- Output raster file. 
+        align -- Input alignment mode; 'auto' resamples the source raster to the grid of the back-link raster. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
         args.append("--source='{}'".format(source))
         args.append("--backlink='{}'".format(backlink))
         args.append("--output='{}'".format(output))
+        args.append("--align={}".format(align))
         return self.run_tool('cost_allocation', args, callback) # returns 1 if error
 
-    def cost_distance(self, source, cost, out_accum, out_backlink, callback=None):
+    def cost_distance(self, source, cost, out_accum, out_backlink, align="none", callback=None):
         """Performs cost-distance accumulation on a cost surface and a group of source cells.
 
         Keyword arguments:
@@ -1996,6 +2726,7 @@ Okay, that's it for now.
         cost -- Input cost (friction) raster file. 
         out_accum -- Output cost accumulation raster file. 
         out_backlink -- Output backlink raster file. 
+        align -- Input alignment mode; 'auto' resamples the source raster to the grid of the cost raster. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
@@ -2003,9 +2734,10 @@ Okay, that's it for now.
         args.append("--cost='{}'".format(cost))
         args.append("--out_accum='{}'".format(out_accum))
         args.append("--out_backlink='{}'".format(out_backlink))
+        args.append("--align={}".format(align))
         return self.run_tool('cost_distance', args, callback) # returns 1 if error
 
-    def cost_pathway(self, destination, backlink, output, zero_background=False, callback=None):
+    def cost_pathway(self, destination, backlink, output, zero_background=False, align="none", callback=None):
         """Performs cost-distance pathway analysis using a series of destination grid cells.
 
         Keyword arguments:
@@ -2014,6 +2746,7 @@ Okay, that's it for now.
         backlink -- Input backlink raster file generated by the cost-distance tool. 
         output -- Output cost pathway raster file. 
         zero_background -- Flag indicating whether zero values should be treated as a background. 
+        align -- Input alignment mode; 'auto' resamples the destination raster to the grid of the back-link raster. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
@@ -2021,6 +2754,7 @@ Okay, that's it for now.
         args.append("--backlink='{}'".format(backlink))
         args.append("--output='{}'".format(output))
         if zero_background: args.append("--zero_background")
+        args.append("--align={}".format(align))
         return self.run_tool('cost_pathway', args, callback) # returns 1 if error
 
     def euclidean_allocation(self, i, output, callback=None):
@@ -2055,6 +2789,30 @@ Okay, that's it for now.
     # GIS Analysis/Overlay Tools #
     ##############################
 
+    def areal_interpolation(self, source, fields, output, target=None, ancillary=None, class_weights=None, cell_size=None, callback=None):
+        """Re-apportions attribute totals from source polygons to target polygons or a raster using area or dasymetric weighting.
+
+        Keyword arguments:
+
+        source -- Input vector polygons file containing the attribute totals. 
+        fields -- Numeric fields to apportion, separated by commas. 
+        target -- Input vector polygons file to which the totals are apportioned; if not specified, the output is a raster. 
+        output -- Output vector polygons file, or raster file if no targets are specified. 
+        ancillary -- Optional ancillary raster used for dasymetric weighting. 
+        class_weights -- Relative densities of the ancillary raster classes, e.g. '1:0.0;2:1.0;3:0.25'. 
+        cell_size -- Grid resolution used for area weighting when no ancillary raster is specified. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--source='{}'".format(source))
+        args.append("--fields='{}'".format(fields))
+        if target is not None: args.append("--target='{}'".format(target))
+        args.append("--output='{}'".format(output))
+        if ancillary is not None: args.append("--ancillary='{}'".format(ancillary))
+        if class_weights is not None: args.append("--class_weights='{}'".format(class_weights))
+        if cell_size is not None: args.append("--cell_size='{}'".format(cell_size))
+        return self.run_tool('areal_interpolation', args, callback) # returns 1 if error
+
     def average_overlay(self, inputs, output, callback=None):
         """Calculates the average for each grid cell from a group of raster images.
 
@@ -2085,7 +2843,7 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('clip', args, callback) # returns 1 if error
 
-    def clip_raster_to_polygon(self, i, polygons, output, maintain_dimensions=True, callback=None):
+    def clip_raster_to_polygon(self, i, polygons, output, maintain_dimensions=True, buffer=0.0, inverse=False, split_features=False, callback=None):
         """Clips a raster to a vector polygon.
 
         Keyword arguments:
@@ -2094,6 +2852,9 @@ Okay, that's it for now.
         polygons -- Input vector polygons file. 
         output -- Output raster file. 
         maintain_dimensions -- Maintain input raster dimensions?. 
+        buffer -- Distance by which the clip mask is grown (positive) or shrunk (negative), in map units. 
+        inverse -- Retain cells outside of the polygons rather than inside?. 
+        split_features -- Clip the input separately to each polygon feature, creating one output per feature?. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
@@ -2101,8 +2862,27 @@ Okay, that's it for now.
         args.append("--polygons='{}'".format(polygons))
         args.append("--output='{}'".format(output))
         if maintain_dimensions: args.append("--maintain_dimensions")
+        args.append("--buffer={}".format(buffer))
+        if inverse: args.append("--inverse")
+        if split_features: args.append("--split_features")
         return self.run_tool('clip_raster_to_polygon', args, callback) # returns 1 if error
 
+    def combine(self, inputs, output, table=None, callback=None):
+        """Assigns a unique ID to each unique combination of values in a group of categorical rasters.
+
+        Keyword arguments:
+
+        inputs -- Input categorical raster files. 
+        output -- Output raster file. 
+        table -- Output cross-reference table (CSV) file; by default, the output raster name with a .csv extension. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--inputs='{}'".format(inputs))
+        args.append("--output='{}'".format(output))
+        if table is not None: args.append("--table='{}'".format(table))
+        return self.run_tool('combine', args, callback) # returns 1 if error
+
     def count_if(self, inputs, output, value, callback=None):
         """Counts the number of occurrences of a specified value in a cell-stack of rasters.
 
@@ -2151,7 +2931,7 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('erase', args, callback) # returns 1 if error
 
-    def erase_polygon_from_raster(self, i, polygons, output, callback=None):
+    def erase_polygon_from_raster(self, i, polygons, output, buffer=0.0, inverse=False, callback=None):
         """Erases (cuts out) a vector polygon from a raster.
 
         Keyword arguments:
@@ -2159,14 +2939,40 @@ Okay, that's it for now.
         i -- Input raster file. 
         polygons -- Input vector polygons file. 
         output -- Output raster file. 
+        buffer -- Distance by which the erase mask is grown (positive) or shrunk (negative), in map units. 
+        inverse -- Erase cells outside of the polygons rather than inside?. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
         args.append("--input='{}'".format(i))
         args.append("--polygons='{}'".format(polygons))
         args.append("--output='{}'".format(output))
+        args.append("--buffer={}".format(buffer))
+        if inverse: args.append("--inverse")
         return self.run_tool('erase_polygon_from_raster', args, callback) # returns 1 if error
 
+    def fill_nodata_from_rasters(self, i, fill_rasters, output, harmonize="local", edge_width=3, out_source=None, callback=None):
+        """Fills NoData gaps in a raster using one or more overlapping rasters in priority order, with optional vertical-offset harmonization.
+
+        Keyword arguments:
+
+        i -- Input primary raster file. 
+        fill_rasters -- Input fill raster files, in priority order. 
+        output -- Output raster file. 
+        harmonize -- Vertical-offset harmonization method; options are 'none', 'global', and 'local'. 
+        edge_width -- Width, in grid cells, of the band of primary data around each gap used to estimate offsets. 
+        out_source -- Optional output raster recording the input from which each cell's value was taken. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--fill_rasters='{}'".format(fill_rasters))
+        args.append("--output='{}'".format(output))
+        args.append("--harmonize={}".format(harmonize))
+        args.append("--edge_width={}".format(edge_width))
+        if out_source is not None: args.append("--out_source='{}'".format(out_source))
+        return self.run_tool('fill_nodata_from_rasters', args, callback) # returns 1 if error
+
     def highest_position(self, inputs, output, callback=None):
         """Identifies the stack position of the maximum value within a raster stack on a cell-by-cell basis.
 
@@ -2775,6 +3581,50 @@ Okay, that's it for now.
         args.append("--filter={}".format(filter))
         return self.run_tool('average_normal_vector_angular_deviation', args, callback) # returns 1 if error
 
+    def bathymetric_position_index(self, dem, output, inner_radius=1, outer_radius=3, standardize=False, callback=None):
+        """Calculates the bathymetric position index (BPI) of a bathymetric DEM using an annulus neighbourhood.
+
+        Keyword arguments:
+
+        dem -- Input raster bathymetric DEM file. 
+        output -- Output raster file. 
+        inner_radius -- Inner radius of the annulus neighbourhood, in grid cells. 
+        outer_radius -- Outer radius of the annulus neighbourhood, in grid cells. 
+        standardize -- Output the standardized BPI (z-score multiplied by 100). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        args.append("--output='{}'".format(output))
+        args.append("--inner_radius={}".format(inner_radius))
+        args.append("--outer_radius={}".format(outer_radius))
+        if standardize: args.append("--standardize")
+        return self.run_tool('bathymetric_position_index', args, callback) # returns 1 if error
+
+    def benthic_terrain_classification(self, dem, dictionary, output, fine_inner=1, fine_outer=3, broad_inner=3, broad_outer=25, callback=None):
+        """Classifies benthic terrain using rules based on fine and broad-scale BPI, slope, and depth.
+
+        Keyword arguments:
+
+        dem -- Input raster bathymetric DEM file. 
+        dictionary -- Input CSV file containing the classification rules. 
+        output -- Output raster file. 
+        fine_inner -- Inner radius of the fine-scale BPI annulus, in grid cells. 
+        fine_outer -- Outer radius of the fine-scale BPI annulus, in grid cells. 
+        broad_inner -- Inner radius of the broad-scale BPI annulus, in grid cells. 
+        broad_outer -- Outer radius of the broad-scale BPI annulus, in grid cells. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        args.append("--dictionary='{}'".format(dictionary))
+        args.append("--output='{}'".format(output))
+        args.append("--fine_inner={}".format(fine_inner))
+        args.append("--fine_outer={}".format(fine_outer))
+        args.append("--broad_inner={}".format(broad_inner))
+        args.append("--broad_outer={}".format(broad_outer))
+        return self.run_tool('benthic_terrain_classification', args, callback) # returns 1 if error
+
     def circular_variance_of_aspect(self, dem, output, filter=11, callback=None):
         """Calculates the circular variance of aspect at a scale for a DEM.
 
@@ -2926,7 +3776,7 @@ Okay, that's it for now.
         args.append("--dem='{}'".format(dem))
         args.append("--output='{}'".format(output))
         args.append("--azimuth={}".format(azimuth))
-        if max_dist is not None: args.append("--max_dist='{}'".format(max_dist))
+        if max_dist is not None: args.append("--max_dist={}".format(max_dist))
         return self.run_tool('directional_relief', args, callback) # returns 1 if error
 
     def downslope_index(self, dem, output, drop=2.0, out_type="tangent", callback=None):
@@ -3249,6 +4099,52 @@ Okay, that's it for now.
         if forms: args.append("--forms")
         return self.run_tool('geomorphons', args, callback) # returns 1 if error
 
+    def glacier_flowlines(self, dem, outlines, output, head_spacing=500.0, min_length=0.0, callback=None):
+        """Extracts glacier flowlines (centerlines) from glacier outlines and a DEM.
+
+        Keyword arguments:
+
+        dem -- Input raster DEM file. 
+        outlines -- Input vector glacier outline polygons file. 
+        output -- Output vector flowlines file. 
+        head_spacing -- Minimum distance between glacier heads, in map units. 
+        min_length -- Minimum length of tributary flowlines, in map units. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        args.append("--outlines='{}'".format(outlines))
+        args.append("--output='{}'".format(output))
+        args.append("--head_spacing={}".format(head_spacing))
+        args.append("--min_length={}".format(min_length))
+        return self.run_tool('glacier_flowlines', args, callback) # returns 1 if error
+
+    def glacier_hypsometry(self, dem, outlines, output, out_hypsometry=None, method="aar", aar=0.58, balance_ratio=1.75, bin_size=50.0, callback=None):
+        """Calculates glacier hypsometry and estimates the equilibrium-line altitude (ELA) and accumulation-area ratio (AAR) of glaciers.
+
+        Keyword arguments:
+
+        dem -- Input raster DEM file. 
+        outlines -- Input vector glacier outline polygons file. 
+        output -- Output vector glacier polygons file. 
+        out_hypsometry -- Output CSV file of binned glacier hypsometry. 
+        method -- ELA estimation method; options are 'aar' and 'aabr'. 
+        aar -- Accumulation-area ratio (0-1) used by the 'aar' method. 
+        balance_ratio -- Ratio of the ablation to accumulation mass-balance gradients used by the 'aabr' method. 
+        bin_size -- Elevation bin size, in DEM z units. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        args.append("--outlines='{}'".format(outlines))
+        args.append("--output='{}'".format(output))
+        if out_hypsometry is not None: args.append("--out_hypsometry='{}'".format(out_hypsometry))
+        args.append("--method={}".format(method))
+        args.append("--aar={}".format(aar))
+        args.append("--balance_ratio={}".format(balance_ratio))
+        args.append("--bin_size={}".format(bin_size))
+        return self.run_tool('glacier_hypsometry', args, callback) # returns 1 if error
+
     def hillshade(self, dem, output, azimuth=315.0, altitude=30.0, zfactor=None, callback=None):
         """Calculates a hillshade raster from an input DEM.
 
@@ -3351,6 +4247,26 @@ Okay, that's it for now.
         if full_mode: args.append("--full_mode")
         return self.run_tool('hypsometrically_tinted_hillshade', args, callback) # returns 1 if error
 
+    def local_dominance(self, dem, output, min_radius=10, max_radius=20, observer_height=1.7, callback=None):
+        """Calculates the local dominance of each cell in a DEM, used for visualizing subtle relief.
+
+        Keyword arguments:
+
+        dem -- Input raster DEM file. 
+        output -- Output raster file. 
+        min_radius -- Inner radius of the annulus of observer cells, in grid cells. 
+        max_radius -- Outer radius of the annulus of observer cells, in grid cells. 
+        observer_height -- Height of the observer's eye above the ground, in vertical units. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        args.append("--output='{}'".format(output))
+        args.append("--min_radius={}".format(min_radius))
+        args.append("--max_radius={}".format(max_radius))
+        args.append("--observer_height={}".format(observer_height))
+        return self.run_tool('local_dominance', args, callback) # returns 1 if error
+
     def local_hypsometric_analysis(self, i, out_mag, out_scale, min_scale=4, step=1, num_steps=10, step_nonlinearity=1.0, callback=None):
         """This tool calculates a local, neighbourhood-based hypsometric integral raster.
 
@@ -3817,22 +4733,24 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('num_upslope_neighbours', args, callback) # returns 1 if error
 
-    def openness(self, i, pos_output, neg_output, dist=20, callback=None):
-        """This tool calculates the topographic openness index from an input DEM.
+    def openness(self, dem, pos_output=None, neg_output=None, max_dist=None, num_directions=16, callback=None):
+        """Calculates the positive and negative topographic openness of a DEM.
 
         Keyword arguments:
 
-        i -- Name of the input raster DEM file. 
-        pos_output -- Name of the positive openness output raster file. 
-        neg_output -- Name of the negative openness output raster file. 
-        dist -- Search distance, in grid cells. 
+        dem -- Input raster DEM file. 
+        pos_output -- Output positive openness raster file. 
+        neg_output -- Output negative openness raster file. 
+        max_dist -- Maximum search distance, in map units; defaults to ten grid cells. 
+        num_directions -- Number of equally spaced search directions. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
-        args.append("--input='{}'".format(i))
-        args.append("--pos_output='{}'".format(pos_output))
-        args.append("--neg_output='{}'".format(neg_output))
-        args.append("--dist={}".format(dist))
+        args.append("--dem='{}'".format(dem))
+        if pos_output is not None: args.append("--pos_output='{}'".format(pos_output))
+        if neg_output is not None: args.append("--neg_output='{}'".format(neg_output))
+        if max_dist is not None: args.append("--max_dist={}".format(max_dist))
+        args.append("--num_directions={}".format(num_directions))
         return self.run_tool('openness', args, callback) # returns 1 if error
 
     def pennock_landform_class(self, dem, output, slope=3.0, prof=0.1, plan=0.0, zfactor=None, callback=None):
@@ -4107,6 +5025,34 @@ Okay, that's it for now.
         args.append("--location={}".format(location))
         return self.run_tool('shadow_image', args, callback) # returns 1 if error
 
+    def shallow_landslide_susceptibility(self, sca, slope, cohesion=0.1, friction_angle=35.0, density_ratio=0.5, transmissivity=50.0, recharge=0.025, out_fs=None, out_rainfall=None, callback=None):
+        """Models shallow landslide susceptibility using the infinite slope stability model and steady-state wetness.
+
+        Keyword arguments:
+
+        sca -- Input raster specific contributing area (SCA) file. 
+        slope -- Input raster slope file (in degrees). 
+        cohesion -- Dimensionless combined root and soil cohesion; a raster file or constant value. 
+        friction_angle -- Soil internal friction angle, in degrees; a raster file or constant value. 
+        density_ratio -- Ratio of the density of water to the density of soil; a raster file or constant value. 
+        transmissivity -- Soil transmissivity (e.g. m^2/day); a raster file or constant value. 
+        recharge -- Steady-state recharge (e.g. m/day); a raster file or constant value. 
+        out_fs -- Output factor of safety raster file. 
+        out_rainfall -- Output critical steady-state rainfall raster file. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--sca='{}'".format(sca))
+        args.append("--slope='{}'".format(slope))
+        args.append("--cohesion='{}'".format(cohesion))
+        args.append("--friction_angle='{}'".format(friction_angle))
+        args.append("--density_ratio='{}'".format(density_ratio))
+        args.append("--transmissivity='{}'".format(transmissivity))
+        args.append("--recharge='{}'".format(recharge))
+        if out_fs is not None: args.append("--out_fs='{}'".format(out_fs))
+        if out_rainfall is not None: args.append("--out_rainfall='{}'".format(out_rainfall))
+        return self.run_tool('shallow_landslide_susceptibility', args, callback) # returns 1 if error
+
     def shape_index(self, dem, output, zfactor=1.0, callback=None):
         """This tool calculates the shape index from an input DEM.
 
@@ -4123,6 +5069,24 @@ Okay, that's it for now.
         args.append("--zfactor={}".format(zfactor))
         return self.run_tool('shape_index', args, callback) # returns 1 if error
 
+    def sky_view_factor(self, dem, output, max_dist=None, num_directions=16, callback=None):
+        """Calculates the sky-view factor of a DEM.
+
+        Keyword arguments:
+
+        dem -- Input raster DEM file. 
+        output -- Output raster file. 
+        max_dist -- Maximum search distance, in map units; defaults to ten grid cells. 
+        num_directions -- Number of equally spaced search directions. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        args.append("--output='{}'".format(output))
+        if max_dist is not None: args.append("--max_dist={}".format(max_dist))
+        args.append("--num_directions={}".format(num_directions))
+        return self.run_tool('sky_view_factor', args, callback) # returns 1 if error
+
     def slope(self, dem, output, zfactor=None, units="degrees", callback=None):
         """Calculates a slope raster from an input DEM.
 
@@ -4141,6 +5105,32 @@ Okay, that's it for now.
         args.append("--units={}".format(units))
         return self.run_tool('slope', args, callback) # returns 1 if error
 
+    def slope_position_classification(self, dem, output, streams=None, out_length=None, slope=3.0, prof=0.1, upper_rp=0.15, lower_rp=0.85, callback=None):
+        """Classifies a DEM into hillslope position elements and calculates hillslope length.
+
+        Keyword arguments:
+
+        dem -- Input raster DEM file. 
+        streams -- Input raster streams file. 
+        output -- Output slope position class raster file. 
+        out_length -- Output hillslope length raster file. 
+        slope -- Slope threshold value, in degrees, below which terrain is considered level. 
+        prof -- Profile curvature threshold value. 
+        upper_rp -- Relative slope position (0-1) below which cells are near the top of the hillslope. 
+        lower_rp -- Relative slope position (0-1) above which cells are near the base of the hillslope. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        if streams is not None: args.append("--streams='{}'".format(streams))
+        args.append("--output='{}'".format(output))
+        if out_length is not None: args.append("--out_length='{}'".format(out_length))
+        args.append("--slope={}".format(slope))
+        args.append("--prof={}".format(prof))
+        args.append("--upper_rp={}".format(upper_rp))
+        args.append("--lower_rp={}".format(lower_rp))
+        return self.run_tool('slope_position_classification', args, callback) # returns 1 if error
+
     def slope_vs_aspect_plot(self, i, output, bin_size=2.0, min_slope=0.1, zfactor=1.0, callback=None):
         """This tool creates a slope-aspect relation plot from an input DEM.
 
@@ -4283,6 +5273,48 @@ Okay, that's it for now.
         if zfactor is not None: args.append("--zfactor='{}'".format(zfactor))
         return self.run_tool('tangential_curvature', args, callback) # returns 1 if error
 
+    def terrain_adjusted_degree_days(self, dem, tdd, fdd, nt=1.0, nf=1.0, ref_elev=None, lapse_rate=6.5, thaw_days=120.0, freeze_days=245.0, radiation=None, rad_coef=0.5, rad_ref=None, rk=1.0, out_tdd=None, out_fdd=None, out_ttop=None, callback=None):
+        """Calculates terrain-adjusted ground-surface thawing and freezing indices and TTOP for permafrost modelling.
+
+        Keyword arguments:
+
+        dem -- Input raster DEM file. 
+        tdd -- Air thawing degree-days; a raster file or constant value. 
+        fdd -- Air freezing degree-days, as positive values; a raster file or constant value. 
+        nt -- Thawing n-factor; a raster file or constant value. 
+        nf -- Freezing n-factor; a raster file or constant value. 
+        ref_elev -- Elevation of the air degree-day measurements; if unspecified, no lapse rate adjustment is applied. 
+        lapse_rate -- Air temperature lapse rate, in degrees C per km. 
+        thaw_days -- Length of the thawing season, in days. 
+        freeze_days -- Length of the freezing season, in days. 
+        radiation -- Input raster of potential incoming solar radiation during the thawing season. 
+        rad_coef -- Sensitivity of thawing degree-days to relative radiation. 
+        rad_ref -- Reference radiation value; defaults to the mean of the radiation raster. 
+        rk -- Ratio of thawed to frozen ground thermal conductivity; a raster file or constant value. 
+        out_tdd -- Output ground-surface thawing index raster file. 
+        out_fdd -- Output ground-surface freezing index raster file. 
+        out_ttop -- Output temperature at the top of permafrost (TTOP) raster file. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        args.append("--tdd='{}'".format(tdd))
+        args.append("--fdd='{}'".format(fdd))
+        args.append("--nt={}".format(nt))
+        args.append("--nf={}".format(nf))
+        if ref_elev is not None: args.append("--ref_elev='{}'".format(ref_elev))
+        args.append("--lapse_rate={}".format(lapse_rate))
+        args.append("--thaw_days={}".format(thaw_days))
+        args.append("--freeze_days={}".format(freeze_days))
+        if radiation is not None: args.append("--radiation='{}'".format(radiation))
+        args.append("--rad_coef={}".format(rad_coef))
+        if rad_ref is not None: args.append("--rad_ref='{}'".format(rad_ref))
+        args.append("--rk={}".format(rk))
+        if out_tdd is not None: args.append("--out_tdd='{}'".format(out_tdd))
+        if out_fdd is not None: args.append("--out_fdd='{}'".format(out_fdd))
+        if out_ttop is not None: args.append("--out_ttop='{}'".format(out_ttop))
+        return self.run_tool('terrain_adjusted_degree_days', args, callback) # returns 1 if error
+
     def time_in_daylight(self, dem, output, lat, long, az_fraction=10.0, max_dist=100.0, utc_offset="00:00", start_day=1, end_day=365, start_time="00:00:00", end_time="23:59:59", callback=None):
         """Calculates the proportion of time a location is not within an area of shadow.
 
@@ -5045,6 +6077,26 @@ Okay, that's it for now.
         if esri_pntr: args.append("--esri_pntr")
         return self.run_tool('flow_length_diff', args, callback) # returns 1 if error
 
+    def hex_flow_accumulation(self, dem, output, out_type="cells", log=False, fill=False, callback=None):
+        """Calculates D6 flow accumulation on a hexagonal grid DEM.
+
+        Keyword arguments:
+
+        dem -- Input hexagonal grid DEM, e.g. created by SquareToHexGrid. 
+        output -- Output hexagonal grid raster file. 
+        out_type -- Output type; one of 'cells' (default), 'catchment area', and 'specific contributing area'. 
+        log -- Optional flag to request the output be log-transformed. 
+        fill -- Optional flag to fill the depressions of the hexagonal grid DEM before routing flow. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        args.append("--output='{}'".format(output))
+        args.append("--out_type={}".format(out_type))
+        if log: args.append("--log")
+        if fill: args.append("--fill")
+        return self.run_tool('hex_flow_accumulation', args, callback) # returns 1 if error
+
     def hillslopes(self, d8_pntr, streams, output, esri_pntr=False, callback=None):
         """Identifies the individual hillslopes draining to each link in a stream network.
 
@@ -5347,6 +6399,36 @@ Okay, that's it for now.
         args.append("--height={}".format(height))
         return self.run_tool('raise_walls', args, callback) # returns 1 if error
 
+    def relative_elevation_model(self, dem, centerline, output, out_trend=None, method="idw", num_points=12, weight=2.0, spacing=None, smoothing=1, max_dist=None, callback=None):
+        """Detrends a DEM relative to the water surface interpolated along a river centerline.
+
+        Keyword arguments:
+
+        dem -- Input raster DEM file. 
+        centerline -- Input vector river centerline file. 
+        output -- Output relative elevation model raster file. 
+        out_trend -- Output water-surface trend raster file. 
+        method -- Trend surface interpolation method; options are 'idw' and 'spline'. 
+        num_points -- Number of nearest centerline samples used to estimate the trend surface. 
+        weight -- IDW weight value. 
+        spacing -- Spacing of centerline samples, in map units; defaults to the grid resolution. 
+        smoothing -- Size of the running median filter applied to centerline samples; 1 for no smoothing. 
+        max_dist -- Maximum distance from the centerline, in map units, of output cells. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--dem='{}'".format(dem))
+        args.append("--centerline='{}'".format(centerline))
+        args.append("--output='{}'".format(output))
+        if out_trend is not None: args.append("--out_trend='{}'".format(out_trend))
+        args.append("--method={}".format(method))
+        args.append("--num_points={}".format(num_points))
+        args.append("--weight={}".format(weight))
+        if spacing is not None: args.append("--spacing='{}'".format(spacing))
+        args.append("--smoothing={}".format(smoothing))
+        if max_dist is not None: args.append("--max_dist='{}'".format(max_dist))
+        return self.run_tool('relative_elevation_model', args, callback) # returns 1 if error
+
     def rho8_flow_accumulation(self, i, output, out_type="specific contributing area", log=False, clip=False, pntr=False, esri_pntr=False, callback=None):
         """This tool calculates Fairfield and Leymarie (1991) flow accumulation.
 
@@ -5421,6 +6503,28 @@ Okay, that's it for now.
         args.append("--snap_dist='{}'".format(snap_dist))
         return self.run_tool('snap_pour_points', args, callback) # returns 1 if error
 
+    def soil_moisture_downscaling(self, i, covariates, output, aspect=None, out_residuals=None, min_coverage=0.5, callback=None):
+        """Downscales coarse soil moisture rasters using fine-resolution topographic covariates.
+
+        Keyword arguments:
+
+        i -- Input coarse-resolution soil moisture raster file. 
+        covariates -- Input fine-resolution covariate raster files (e.g. wetness index, slope). 
+        aspect -- Optional fine-resolution aspect raster file, in degrees. 
+        output -- Output fine-resolution soil moisture raster file. 
+        out_residuals -- Optional output interpolated residuals raster file. 
+        min_coverage -- Minimum fraction of valid fine cells needed to include a coarse cell in the model. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--covariates='{}'".format(covariates))
+        if aspect is not None: args.append("--aspect='{}'".format(aspect))
+        args.append("--output='{}'".format(output))
+        if out_residuals is not None: args.append("--out_residuals='{}'".format(out_residuals))
+        args.append("--min_coverage={}".format(min_coverage))
+        return self.run_tool('soil_moisture_downscaling', args, callback) # returns 1 if error
+
     def stochastic_depression_analysis(self, dem, output, rmse, range, iterations=100, callback=None):
         """Performs a stochastic analysis of depressions within a DEM.
 
@@ -5477,6 +6581,42 @@ Okay, that's it for now.
         if esri_pntr: args.append("--esri_pntr")
         return self.run_tool('subbasins', args, callback) # returns 1 if error
 
+    def topo_to_raster(self, output, contours=None, contour_field="ELEV", points=None, point_field="ELEV", streams=None, lakes=None, sinks=None, cell_size=None, base=None, smoothing=0.5, max_iterations=500, tolerance=0.001, callback=None):
+        """Interpolates a hydrologically correct DEM from contours, spot heights, streams, and lakes.
+
+        Keyword arguments:
+
+        contours -- Input vector contour lines file. 
+        contour_field -- Name of the contour elevation attribute field. 
+        points -- Input vector spot heights (points) file. 
+        point_field -- Name of the spot height elevation attribute field. 
+        streams -- Input vector stream lines file. 
+        lakes -- Input vector lake polygons file. 
+        sinks -- Input vector points file of known sinks. 
+        output -- Output raster DEM file. 
+        cell_size -- Optionally specified cell size of output raster. Not used when base raster is specified. 
+        base -- Optionally specified input base raster file. Not used when a cell size is specified. 
+        smoothing -- Weight of the thin plate (second-derivative) roughness penalty, from 0 to 1. 
+        max_iterations -- Maximum number of iterations at each grid level. 
+        tolerance -- Iteration stops when the largest elevation change is less than this value. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        if contours is not None: args.append("--contours='{}'".format(contours))
+        args.append("--contour_field={}".format(contour_field))
+        if points is not None: args.append("--points='{}'".format(points))
+        args.append("--point_field={}".format(point_field))
+        if streams is not None: args.append("--streams='{}'".format(streams))
+        if lakes is not None: args.append("--lakes='{}'".format(lakes))
+        if sinks is not None: args.append("--sinks='{}'".format(sinks))
+        args.append("--output='{}'".format(output))
+        if cell_size is not None: args.append("--cell_size='{}'".format(cell_size))
+        if base is not None: args.append("--base='{}'".format(base))
+        args.append("--smoothing={}".format(smoothing))
+        args.append("--max_iterations={}".format(max_iterations))
+        args.append("--tolerance={}".format(tolerance))
+        return self.run_tool('topo_to_raster', args, callback) # returns 1 if error
+
     def trace_downslope_flowpaths(self, seed_pts, d8_pntr, output, esri_pntr=False, zero_background=False, callback=None):
         """Traces downslope flowpaths from one or more target sites (i.e. seed points).
 
@@ -5551,6 +6691,44 @@ Okay, that's it for now.
     # Image Processing Tools #
     ##########################
 
+    def burn_severity(self, output, pre_nir=None, pre_swir=None, pre_nbr=None, post_nir=None, post_swir=None, post_nbr=None, index="dnbr", offset=0.0, breaks=None, names=None, out_classes=None, polygons=None, out_stats=None, callback=None):
+        """Maps wildfire burn severity classes from pre- and post-fire imagery using dNBR or RdNBR.
+
+        Keyword arguments:
+
+        pre_nir -- Input pre-fire near-infrared band raster file. 
+        pre_swir -- Input pre-fire shortwave-infrared (approx. 2.2 um) band raster file. 
+        pre_nbr -- Input pre-fire normalized burn ratio raster file, used instead of the bands. 
+        post_nir -- Input post-fire near-infrared band raster file. 
+        post_swir -- Input post-fire shortwave-infrared (approx. 2.2 um) band raster file. 
+        post_nbr -- Input post-fire normalized burn ratio raster file, used instead of the bands. 
+        output -- Output dNBR or RdNBR raster file. 
+        index -- Severity index; either 'dnbr' or 'rdnbr'. 
+        offset -- Mean dNBR of unburned areas, subtracted from the dNBR. 
+        breaks -- Ascending severity class breaks, e.g. '100;270;440;660'; defaults to the standard breaks of the index. 
+        names -- Names of the severity classes, one more than the number of breaks. 
+        out_classes -- Optional output severity class raster file. 
+        polygons -- Optional output severity class polygons file; requires --out_classes. 
+        out_stats -- Optional output CSV file of the area of each severity class. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        if pre_nir is not None: args.append("--pre_nir='{}'".format(pre_nir))
+        if pre_swir is not None: args.append("--pre_swir='{}'".format(pre_swir))
+        if pre_nbr is not None: args.append("--pre_nbr='{}'".format(pre_nbr))
+        if post_nir is not None: args.append("--post_nir='{}'".format(post_nir))
+        if post_swir is not None: args.append("--post_swir='{}'".format(post_swir))
+        if post_nbr is not None: args.append("--post_nbr='{}'".format(post_nbr))
+        args.append("--output='{}'".format(output))
+        args.append("--index={}".format(index))
+        args.append("--offset={}".format(offset))
+        if breaks is not None: args.append("--breaks='{}'".format(breaks))
+        if names is not None: args.append("--names='{}'".format(names))
+        if out_classes is not None: args.append("--out_classes='{}'".format(out_classes))
+        if polygons is not None: args.append("--polygons='{}'".format(polygons))
+        if out_stats is not None: args.append("--out_stats='{}'".format(out_stats))
+        return self.run_tool('burn_severity', args, callback) # returns 1 if error
+
     def change_vector_analysis(self, date1, date2, magnitude, direction, callback=None):
         """Performs a change vector analysis on a two-date multi-spectral dataset.
 
@@ -5587,6 +6765,36 @@ Okay, that's it for now.
         args.append("--filtery={}".format(filtery))
         return self.run_tool('closing', args, callback) # returns 1 if error
 
+    def colourize_raster(self, i, output, palette="viridis", reverse=False, min_value=None, max_value=None, clip=0.0, hillshade=None, blend=0.5, align="none", callback=None):
+        """Renders a single-band raster as a colour image (RGBA raster or PNG) using a colour ramp.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output colour raster or PNG (.png) file. 
+        palette -- Built-in colour ramp name (e.g. 'viridis', 'terrain') or palette file. 
+        reverse -- Optional flag to reverse the colours of the palette. 
+        min_value -- Value at the low end of the colour ramp. 
+        max_value -- Value at the high end of the colour ramp. 
+        clip -- Percentage of cells clipped from each tail of the distribution to find the range of values. 
+        hillshade -- Input hillshade raster blended with the colours. 
+        blend -- Weight (0-1) of the hillshade in the blended image. 
+        align -- Input alignment mode; 'auto' resamples the hillshade to the grid of the input. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--palette={}".format(palette))
+        if reverse: args.append("--reverse")
+        if min_value is not None: args.append("--min_value='{}'".format(min_value))
+        if max_value is not None: args.append("--max_value='{}'".format(max_value))
+        args.append("--clip={}".format(clip))
+        if hillshade is not None: args.append("--hillshade='{}'".format(hillshade))
+        args.append("--blend={}".format(blend))
+        args.append("--align={}".format(align))
+        return self.run_tool('colourize_raster', args, callback) # returns 1 if error
+
     def create_colour_composite(self, red, green, blue, output, opacity=None, enhance=True, zeros=False, callback=None):
         """Creates a colour-composite image from three bands of multispectral imagery.
 
@@ -5611,6 +6819,68 @@ Okay, that's it for now.
         if zeros: args.append("--zeros")
         return self.run_tool('create_colour_composite', args, callback) # returns 1 if error
 
+    def export_colour_composite(self, red, green, blue, output, stretch="percent", clip=2.0, num_stdev=2.0, min_values=None, max_values=None, gamma=1.0, align="none", callback=None):
+        """Creates a colour composite image (RGBA raster or PNG) from three bands, with a contrast stretch of each band.
+
+        Keyword arguments:
+
+        red -- Input red band image file. 
+        green -- Input green band image file. 
+        blue -- Input blue band image file. 
+        output -- Output colour raster or PNG (.png) file. 
+        stretch -- Contrast stretch of each band; one of 'none', 'minmax', 'percent', and 'stdev'. 
+        clip -- Percentage of cells clipped from each tail of a band's distribution by the percent stretch. 
+        num_stdev -- Number of standard deviations either side of the mean spanned by the stdev stretch. 
+        min_values -- Values of the red, green and blue bands stretched to zero intensity. 
+        max_values -- Values of the red, green and blue bands stretched to full intensity. 
+        gamma -- Gamma correction of the stretched intensities. 
+        align -- Input alignment mode; 'auto' resamples the green and blue bands to the grid of the red band. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--red='{}'".format(red))
+        args.append("--green='{}'".format(green))
+        args.append("--blue='{}'".format(blue))
+        args.append("--output='{}'".format(output))
+        args.append("--stretch={}".format(stretch))
+        args.append("--clip={}".format(clip))
+        args.append("--num_stdev={}".format(num_stdev))
+        if min_values is not None: args.append("--min_values='{}'".format(min_values))
+        if max_values is not None: args.append("--max_values='{}'".format(max_values))
+        args.append("--gamma={}".format(gamma))
+        args.append("--align={}".format(align))
+        return self.run_tool('export_colour_composite', args, callback) # returns 1 if error
+
+    def export_web_tiles(self, i, output, format=None, min_zoom=None, max_zoom=None, palette="viridis", reverse=False, min_value=None, max_value=None, clip=0.0, callback=None):
+        """Renders a raster into a pyramid of Web Mercator map tiles (XYZ, TMS or MBTiles).
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output MBTiles (.mbtiles) file or tile directory. 
+        format -- Output tile format; one of 'xyz', 'tms', and 'mbtiles'. 
+        min_zoom -- Lowest zoom level of the tiles. 
+        max_zoom -- Highest zoom level of the tiles. 
+        palette -- Built-in colour ramp name (e.g. 'viridis', 'terrain') or palette file, for inputs that are not RGB. 
+        reverse -- Optional flag to reverse the colours of the palette. 
+        min_value -- Value at the low end of the colour ramp. 
+        max_value -- Value at the high end of the colour ramp. 
+        clip -- Percentage of cells clipped from each tail of the distribution to find the range of values. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if format is not None: args.append("--format='{}'".format(format))
+        if min_zoom is not None: args.append("--min_zoom='{}'".format(min_zoom))
+        if max_zoom is not None: args.append("--max_zoom='{}'".format(max_zoom))
+        args.append("--palette={}".format(palette))
+        if reverse: args.append("--reverse")
+        if min_value is not None: args.append("--min_value='{}'".format(min_value))
+        if max_value is not None: args.append("--max_value='{}'".format(max_value))
+        args.append("--clip={}".format(clip))
+        return self.run_tool('export_web_tiles', args, callback) # returns 1 if error
+
     def flip_image(self, i, output, direction="vertical", callback=None):
         """Reflects an image in the vertical or horizontal axis.
 
@@ -5627,6 +6897,38 @@ Okay, that's it for now.
         args.append("--direction={}".format(direction))
         return self.run_tool('flip_image', args, callback) # returns 1 if error
 
+    def hough_transform(self, i, output, feature="lines", theta_res=1.0, min_votes=20, min_length=None, max_gap=None, min_radius=None, max_radius=None, min_score=0.5, max_features=100, callback=None):
+        """Detects linear or circular features in a binary edge raster using the Hough transform.
+
+        Keyword arguments:
+
+        i -- Input binary edge raster file. 
+        output -- Output vector file of the detected lines or circles. 
+        feature -- Type of feature to detect; either 'lines' or 'circles'. 
+        theta_res -- Angular resolution of the line accumulator, in degrees. 
+        min_votes -- Minimum number of edge cells voting for a line. 
+        min_length -- Minimum length of an output line segment, in map units; defaults to 10 cells. 
+        max_gap -- Maximum gap between the edge cells of a line segment, in map units; defaults to 2 cells. 
+        min_radius -- Minimum circle radius, in map units. 
+        max_radius -- Maximum circle radius, in map units. 
+        min_score -- Minimum fraction (0-1) of a circle's circumference that must be edge cells. 
+        max_features -- Maximum number of output features. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--feature={}".format(feature))
+        args.append("--theta_res={}".format(theta_res))
+        args.append("--min_votes={}".format(min_votes))
+        if min_length is not None: args.append("--min_length='{}'".format(min_length))
+        if max_gap is not None: args.append("--max_gap='{}'".format(max_gap))
+        if min_radius is not None: args.append("--min_radius='{}'".format(min_radius))
+        if max_radius is not None: args.append("--max_radius='{}'".format(max_radius))
+        args.append("--min_score={}".format(min_score))
+        args.append("--max_features={}".format(max_features))
+        return self.run_tool('hough_transform', args, callback) # returns 1 if error
+
     def ihs_to_rgb(self, intensity, hue, saturation, red=None, green=None, blue=None, output=None, callback=None):
         """Converts intensity, hue, and saturation (IHS) images into red, green, and blue (RGB) images.
 
@@ -5692,38 +6994,80 @@ Okay, that's it for now.
         callback -- Custom function for handling tool text outputs.
         """
         args = []
-        args.append("--inputs='{}'".format(inputs))
-        args.append("--points='{}'".format(points))
+        args.append("--inputs='{}'".format(inputs))
+        args.append("--points='{}'".format(points))
+        args.append("--output='{}'".format(output))
+        return self.run_tool('image_stack_profile', args, callback) # returns 1 if error
+
+    def integral_image(self, i, output, callback=None):
+        """Transforms an input image (summed area table) into its integral image equivalent.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output raster file. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        return self.run_tool('integral_image', args, callback) # returns 1 if error
+
+    def line_thinning(self, i, output, callback=None):
+        """Performs line thinning a on Boolean raster image; intended to be used with the RemoveSpurs tool.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output raster file. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
         args.append("--output='{}'".format(output))
-        return self.run_tool('image_stack_profile', args, callback) # returns 1 if error
+        return self.run_tool('line_thinning', args, callback) # returns 1 if error
 
-    def integral_image(self, i, output, callback=None):
-        """Transforms an input image (summed area table) into its integral image equivalent.
+    def morphological_filter(self, i, output, operation="opening", shape="square", size=3, element=None, binary=False, callback=None):
+        """Applies a morphological operator with an arbitrary structuring element to a binary or greyscale image.
 
         Keyword arguments:
 
         i -- Input raster file. 
         output -- Output raster file. 
+        operation -- Morphological operation; one of 'erosion', 'dilation', 'opening', 'closing', 'white tophat', and 'black tophat'. 
+        shape -- Shape of the structuring element; one of 'square', 'disk', 'diamond', 'cross', and 'custom'. 
+        size -- Width of the structuring element, in cells (odd). 
+        element -- Text file of the custom structuring element, with non-zero values for its members. 
+        binary -- Optional flag to treat the input as a binary mask of non-zero cells. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
         args.append("--input='{}'".format(i))
         args.append("--output='{}'".format(output))
-        return self.run_tool('integral_image', args, callback) # returns 1 if error
+        args.append("--operation={}".format(operation))
+        args.append("--shape={}".format(shape))
+        args.append("--size={}".format(size))
+        if element is not None: args.append("--element='{}'".format(element))
+        if binary: args.append("--binary")
+        return self.run_tool('morphological_filter', args, callback) # returns 1 if error
 
-    def line_thinning(self, i, output, callback=None):
-        """Performs line thinning a on Boolean raster image; intended to be used with the RemoveSpurs tool.
+    def morphological_reconstruction(self, marker, mask, output, operation="dilation", callback=None):
+        """Performs a morphological reconstruction by dilation or erosion of a marker image under a mask image.
 
         Keyword arguments:
 
-        i -- Input raster file. 
+        marker -- Input marker raster file. 
+        mask -- Input mask raster file. 
         output -- Output raster file. 
+        operation -- Reconstruction by 'dilation' or by 'erosion'. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
-        args.append("--input='{}'".format(i))
+        args.append("--marker='{}'".format(marker))
+        args.append("--mask='{}'".format(mask))
         args.append("--output='{}'".format(output))
-        return self.run_tool('line_thinning', args, callback) # returns 1 if error
+        args.append("--operation={}".format(operation))
+        return self.run_tool('morphological_reconstruction', args, callback) # returns 1 if error
 
     def mosaic(self, output, inputs=None, method="nn", callback=None):
         """Mosaics two or more images together.
@@ -5859,6 +7203,66 @@ Okay, that's it for now.
         args.append("--saturation='{}'".format(saturation))
         return self.run_tool('rgb_to_ihs', args, callback) # returns 1 if error
 
+    def sar_decibel_conversion(self, i, output, direction="to_db", amplitude=False, callback=None):
+        """Converts SAR backscatter between linear intensity or amplitude and decibels.
+
+        Keyword arguments:
+
+        i -- Input SAR backscatter raster file. 
+        output -- Output raster file. 
+        direction -- Direction of the conversion; either 'to_db' or 'to_linear'. 
+        amplitude -- Optional flag indicating that the linear values are amplitudes rather than intensities. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--direction={}".format(direction))
+        if amplitude: args.append("--amplitude")
+        return self.run_tool('sar_decibel_conversion', args, callback) # returns 1 if error
+
+    def skeletonize(self, i, output, width=None, callback=None):
+        """Thins the features of a binary raster to their skeletons and optionally maps their widths.
+
+        Keyword arguments:
+
+        i -- Input binary raster file. 
+        output -- Output skeleton raster file. 
+        width -- Optional output raster file of the feature widths at the skeleton cells. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if width is not None: args.append("--width='{}'".format(width))
+        return self.run_tool('skeletonize', args, callback) # returns 1 if error
+
+    def spectral_indices(self, inputs, indices="NDVI", sensor=None, bands=None, scale=1.0, offset=0.0, outdir=None, prefix=None, callback=None):
+        """Calculates one or more named spectral indices (NDVI, NDWI, NBR, EVI...) from the bands of a multispectral image.
+
+        Keyword arguments:
+
+        inputs -- Input band raster files, in band order. 
+        sensor -- Sensor band numbering preset (optional). 
+        bands -- Band numbers of named bands, e.g. 'red=4;nir=5', overriding the sensor preset (optional). 
+        indices -- Names of the indices to calculate, e.g. 'NDVI;NBR', or 'all'. 
+        scale -- Scale factor used to convert band values to reflectance. 
+        offset -- Offset used to convert band values to reflectance. 
+        outdir -- Output directory (optional; defaults to the working directory). 
+        prefix -- Output file name prefix (optional). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--inputs='{}'".format(inputs))
+        if sensor is not None: args.append("--sensor='{}'".format(sensor))
+        if bands is not None: args.append("--bands='{}'".format(bands))
+        args.append("--indices='{}'".format(indices))
+        args.append("--scale={}".format(scale))
+        args.append("--offset={}".format(offset))
+        if outdir is not None: args.append("--outdir='{}'".format(outdir))
+        if prefix is not None: args.append("--prefix='{}'".format(prefix))
+        return self.run_tool('spectral_indices', args, callback) # returns 1 if error
+
     def split_colour_composite(self, i, red=None, green=None, blue=None, callback=None):
         """This tool splits an RGB colour composite image into separate multispectral images.
 
@@ -5877,6 +7281,28 @@ Okay, that's it for now.
         if blue is not None: args.append("--blue='{}'".format(blue))
         return self.run_tool('split_colour_composite', args, callback) # returns 1 if error
 
+    def subpixel_boundary(self, i, output, classes=None, threshold=None, search=2, smooth=3, callback=None):
+        """Extracts class boundaries, such as shorelines, from a continuous band to sub-pixel precision as vector lines.
+
+        Keyword arguments:
+
+        i -- Input continuous band raster file, e.g. a water index. 
+        classes -- Optional input binary classification raster file; positive values are the class of interest. 
+        output -- Output vector lines file. 
+        threshold -- Band value of the boundary; required if no classification raster is specified. 
+        search -- Half-width of the search window around the classified boundary, in cells. 
+        smooth -- Size of the moving average applied to the line vertices (odd; 1 for none). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        if classes is not None: args.append("--classes='{}'".format(classes))
+        args.append("--output='{}'".format(output))
+        if threshold is not None: args.append("--threshold='{}'".format(threshold))
+        args.append("--search={}".format(search))
+        args.append("--smooth={}".format(smooth))
+        return self.run_tool('subpixel_boundary', args, callback) # returns 1 if error
+
     def thicken_raster_line(self, i, output, callback=None):
         """Thickens single-cell wide lines within a raster image.
 
@@ -6045,6 +7471,38 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('parallelepiped_classification', args, callback) # returns 1 if error
 
+    def surface_water_extraction(self, i, output, polygons=None, water="low", method="kittler", threshold=None, hand=None, max_hand=15.0, incidence=None, max_incidence=80.0, min_area=None, callback=None):
+        """Maps surface water by automatically thresholding a SAR or water index band, with optional terrain masking.
+
+        Keyword arguments:
+
+        i -- Input SAR backscatter or water index raster file. 
+        output -- Output water mask raster file. 
+        polygons -- Optional output vector polygons file of the water areas. 
+        water -- Whether water has 'low' (SAR backscatter) or 'high' (water index) band values. 
+        method -- Automatic thresholding method; either 'kittler' or 'otsu'. 
+        threshold -- Optional band threshold, which overrides the automatic threshold. 
+        hand -- Optional input height above nearest drainage raster file. 
+        max_hand -- Maximum height above nearest drainage of water cells. 
+        incidence -- Optional input local incidence angle raster file, in degrees. 
+        max_incidence -- Maximum local incidence angle of water cells, in degrees. 
+        min_area -- Minimum area of a water body, in map units squared. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        if polygons is not None: args.append("--polygons='{}'".format(polygons))
+        args.append("--water={}".format(water))
+        args.append("--method={}".format(method))
+        if threshold is not None: args.append("--threshold='{}'".format(threshold))
+        if hand is not None: args.append("--hand='{}'".format(hand))
+        args.append("--max_hand={}".format(max_hand))
+        if incidence is not None: args.append("--incidence='{}'".format(incidence))
+        args.append("--max_incidence={}".format(max_incidence))
+        if min_area is not None: args.append("--min_area='{}'".format(min_area))
+        return self.run_tool('surface_water_extraction', args, callback) # returns 1 if error
+
     ##################################
     # Image Processing Tools/Filters #
     ##################################
@@ -6525,6 +7983,34 @@ Okay, that's it for now.
         args.append("--clip={}".format(clip))
         return self.run_tool('prewitt_filter', args, callback) # returns 1 if error
 
+    def quick_view(self, output, i=None, vectors=None, palette="viridis", reverse=False, clip=1.0, hillshade=False, blend=0.5, max_size=512, callback=None):
+        """Renders a small preview image (PNG) of a raster, optionally hillshaded, with vector overlays.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        vectors -- Input vector files drawn over the raster. 
+        output -- Output PNG (.png) file. 
+        palette -- Built-in colour ramp name (e.g. 'viridis', 'terrain') or palette file. 
+        reverse -- Optional flag to reverse the colours of the palette. 
+        clip -- Percentage of cells clipped from each tail of the distribution to find the range of values. 
+        hillshade -- Optional flag to blend the colours with a hillshade of the raster. 
+        blend -- Weight (0-1) of the hillshade in the blended image. 
+        max_size -- Maximum width and height of the image, in pixels. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        if i is not None: args.append("--input='{}'".format(i))
+        if vectors is not None: args.append("--vectors='{}'".format(vectors))
+        args.append("--output='{}'".format(output))
+        args.append("--palette={}".format(palette))
+        if reverse: args.append("--reverse")
+        args.append("--clip={}".format(clip))
+        if hillshade: args.append("--hillshade")
+        args.append("--blend={}".format(blend))
+        args.append("--max_size={}".format(max_size))
+        return self.run_tool('quick_view', args, callback) # returns 1 if error
+
     def range_filter(self, i, output, filterx=11, filtery=11, callback=None):
         """Assigns each cell in the output grid the range of values in a moving window centred on each grid cell in the input raster.
 
@@ -6559,6 +8045,30 @@ Okay, that's it for now.
         args.append("--clip={}".format(clip))
         return self.run_tool('roberts_cross_filter', args, callback) # returns 1 if error
 
+    def sar_speckle_filter(self, i, output, method="enhanced lee", filter=7, looks=1.0, damping=1.0, db=False, callback=None):
+        """Reduces speckle in SAR backscatter imagery using the Lee, enhanced Lee, Frost, or Gamma-MAP filters.
+
+        Keyword arguments:
+
+        i -- Input SAR backscatter raster file. 
+        output -- Output raster file. 
+        method -- Speckle filter; one of 'lee', 'enhanced lee', 'frost', and 'gamma map'. 
+        filter -- Size of the square filter window, in cells (odd). 
+        looks -- Equivalent number of looks (ENL) of the image. 
+        damping -- Damping factor of the enhanced Lee and Frost filters. 
+        db -- Optional flag indicating that the input backscatter is in decibels. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--method={}".format(method))
+        args.append("--filter={}".format(filter))
+        args.append("--looks={}".format(looks))
+        args.append("--damping={}".format(damping))
+        if db: args.append("--db")
+        return self.run_tool('sar_speckle_filter', args, callback) # returns 1 if error
+
     def scharr_filter(self, i, output, clip=0.0, callback=None):
         """Performs a Scharr edge-detection filter on an image.
 
@@ -6929,6 +8439,34 @@ Okay, that's it for now.
         args.append("--num_tones={}".format(num_tones))
         return self.run_tool('standard_deviation_contrast_stretch', args, callback) # returns 1 if error
 
+    def topographic_correction(self, i, dem, output, azimuth, altitude, method="c", zfactor=None, shadow_mask=None, align="none", callback=None):
+        """Corrects the topographic illumination of an image band using a DEM and the solar geometry, and detects shadows.
+
+        Keyword arguments:
+
+        i -- Input image band raster file. 
+        dem -- Input raster DEM file. 
+        output -- Output corrected image raster file. 
+        azimuth -- Solar azimuth at the time of image acquisition, in degrees clockwise from north. 
+        altitude -- Solar altitude (elevation above the horizon) at the time of image acquisition, in degrees. 
+        method -- Correction method; one of 'c', 'minnaert', and 'cosine'. 
+        zfactor -- Optional multiplier for when the vertical and horizontal units are not the same. 
+        shadow_mask -- Optional output shadow raster file (0 = illuminated, 1 = self-shadow, 2 = cast shadow). 
+        align -- Input alignment mode; 'auto' resamples the DEM to the grid of the image. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--dem='{}'".format(dem))
+        args.append("--output='{}'".format(output))
+        args.append("--azimuth={}".format(azimuth))
+        args.append("--altitude={}".format(altitude))
+        args.append("--method={}".format(method))
+        if zfactor is not None: args.append("--zfactor='{}'".format(zfactor))
+        if shadow_mask is not None: args.append("--shadow_mask='{}'".format(shadow_mask))
+        args.append("--align={}".format(align))
+        return self.run_tool('topographic_correction', args, callback) # returns 1 if error
+
     ###############
     # LiDAR Tools #
     ###############
@@ -7541,16 +9079,17 @@ Okay, that's it for now.
         if maxz is not None: args.append("--maxz='{}'".format(maxz))
         return self.run_tool('lidar_idw_interpolation', args, callback) # returns 1 if error
 
-    def lidar_info(self, i, output, density=True, vlr=True, geokeys=True, callback=None):
+    def lidar_info(self, i, output, density=True, vlr=True, geokeys=True, validate=False, callback=None):
         """Prints information about a LiDAR (LAS) dataset, including header, point return frequency, and classification data and information about the variable length records (VLRs) and geokeys.
 
         Keyword arguments:
 
         i -- Input LiDAR file. 
-        output -- Output HTML file for summary report. 
+        output -- Output HTML or JSON file for summary report. 
         density -- Flag indicating whether or not to calculate the average point density and nominal point spacing. 
         vlr -- Flag indicating whether or not to print the variable length records (VLRs). 
         geokeys -- Flag indicating whether or not to print the geokeys. 
+        validate -- Flag indicating whether or not to check the file for anomalies. 
         callback -- Custom function for handling tool text outputs.
         """
         args = []
@@ -7559,6 +9098,7 @@ Okay, that's it for now.
         if density: args.append("--density")
         if vlr: args.append("--vlr")
         if geokeys: args.append("--geokeys")
+        if validate: args.append("--validate")
         return self.run_tool('lidar_info', args, callback) # returns 1 if error
 
     def lidar_join(self, inputs, output, callback=None):
@@ -8205,6 +9745,44 @@ Okay, that's it for now.
         args.append("--min_points={}".format(min_points))
         return self.run_tool('dbscan', args, callback) # returns 1 if error
 
+    def export_image_chips(self, inputs, outdir, labels=None, label_polys=None, field="CLASS", chip_size=256, stride=None, augment="none", background=0, min_labelled=0.0, max_nodata=0.0, max_per_class=None, seed=42, align="none", callback=None):
+        """Exports image chips and label masks from a multiband image for training deep learning models.
+
+        Keyword arguments:
+
+        inputs -- Input image band raster files. 
+        labels -- Input raster of class values; either this or a label polygon file is required. 
+        label_polys -- Input polygon vector of classes; either this or a label raster is required. 
+        field -- Numeric class field of the label polygons. 
+        outdir -- Output directory. 
+        chip_size -- Width and height of the chips, in grid cells. 
+        stride -- Spacing of the chips, in grid cells; defaults to the chip size. 
+        augment -- Transformed copies of each chip to export; one of 'none', 'flips', 'rotations', and 'all'. 
+        background -- Class value of unlabelled cells. 
+        min_labelled -- Minimum fraction (0-1) of a chip's cells with a class other than the background. 
+        max_nodata -- Maximum fraction (0-1) of a chip's cells that may be NoData. 
+        max_per_class -- Maximum number of chips exported for each dominant class. 
+        seed -- Seed of the random selection of chips when there is a maximum per class. 
+        align -- Input alignment mode; 'auto' resamples the other inputs to the grid of the first band. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--inputs='{}'".format(inputs))
+        if labels is not None: args.append("--labels='{}'".format(labels))
+        if label_polys is not None: args.append("--label_polys='{}'".format(label_polys))
+        args.append("--field={}".format(field))
+        args.append("--outdir='{}'".format(outdir))
+        args.append("--chip_size={}".format(chip_size))
+        if stride is not None: args.append("--stride='{}'".format(stride))
+        args.append("--augment={}".format(augment))
+        args.append("--background={}".format(background))
+        args.append("--min_labelled={}".format(min_labelled))
+        args.append("--max_nodata={}".format(max_nodata))
+        if max_per_class is not None: args.append("--max_per_class='{}'".format(max_per_class))
+        args.append("--seed={}".format(seed))
+        args.append("--align={}".format(align))
+        return self.run_tool('export_image_chips', args, callback) # returns 1 if error
+
     def k_means_clustering(self, inputs, output, classes, out_html=None, max_iterations=10, class_change=2.0, initialize="diagonal", min_class_size=10, callback=None):
         """Performs a k-means clustering operation on a multi-spectral dataset.
 
@@ -8329,6 +9907,42 @@ Okay, that's it for now.
         args.append("--class_change={}".format(class_change))
         return self.run_tool('modified_k_means_clustering', args, callback) # returns 1 if error
 
+    def onnx_inference(self, inputs, model, output, mode="patch", patch_size=256, overlap=32, batch_size=1024, output_type="class", channel=0, mean=None, std=None, channels_last=False, align="none", callback=None):
+        """Applies an ONNX machine learning model to a multiband raster stack.
+
+        Keyword arguments:
+
+        inputs -- Input image band raster files, in the order of the model's input channels. 
+        model -- Input ONNX model file (*.onnx). 
+        output -- Output raster file. 
+        mode -- Inference mode; 'pixel' applies the model to each cell, 'patch' to square patches. 
+        patch_size -- Width and height of the model's input patches, in grid cells. 
+        overlap -- Overlap of neighbouring patches, in grid cells. 
+        batch_size -- Number of cells passed to the model at a time in pixel mode. 
+        output_type -- Output type; 'class' outputs the class with the highest score, 'value' a model output. 
+        channel -- Model output (channel) written to a value output, numbered from 0. 
+        mean -- Normalization means, one per band or a single value, e.g. '0.48,0.46,0.41'. 
+        std -- Normalization standard deviations, one per band or a single value. 
+        channels_last -- Are the model's patches in (1, rows, columns, bands) order?. 
+        align -- Input alignment mode; 'auto' resamples the other inputs to the grid of the first band. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--inputs='{}'".format(inputs))
+        args.append("--model='{}'".format(model))
+        args.append("--output='{}'".format(output))
+        args.append("--mode={}".format(mode))
+        args.append("--patch_size={}".format(patch_size))
+        args.append("--overlap={}".format(overlap))
+        args.append("--batch_size={}".format(batch_size))
+        args.append("--output_type={}".format(output_type))
+        args.append("--channel={}".format(channel))
+        if mean is not None: args.append("--mean='{}'".format(mean))
+        if std is not None: args.append("--std='{}'".format(std))
+        if channels_last: args.append("--channels_last")
+        args.append("--align={}".format(align))
+        return self.run_tool('onnx_inference', args, callback) # returns 1 if error
+
     def random_forest_classification(self, inputs, training, field, output=None, split_criterion="Gini", n_trees=500, min_samples_leaf=1, min_samples_split=2, test_proportion=0.2, callback=None):
         """Performs a supervised random forest classification using training site polygons/points and predictor rasters.
 
@@ -8903,6 +10517,32 @@ Okay, that's it for now.
         args.append("--output='{}'".format(output))
         return self.run_tool('floor', args, callback) # returns 1 if error
 
+    def global_autocorrelation(self, i, field, output, weights="distance", distance=None, k=8, permutations=999, threshold=None, callback=None):
+        """Calculates Moran's I, Geary's C, and join count statistics for a vector attribute, with permutation inference.
+
+        Keyword arguments:
+
+        i -- Input vector file. 
+        field -- Numeric field to analyze. 
+        output -- Output HTML or JSON report file. 
+        weights -- Method used to define neighbours; options are 'contiguity', 'distance' and 'knn'. 
+        distance -- Distance band used with distance weights. 
+        k -- Number of nearest neighbours used with knn weights. 
+        permutations -- Number of random permutations used to evaluate significance. 
+        threshold -- Values greater than or equal to the threshold are 'black' in the join count analysis; the default is the mean. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--field='{}'".format(field))
+        args.append("--output='{}'".format(output))
+        args.append("--weights={}".format(weights))
+        if distance is not None: args.append("--distance='{}'".format(distance))
+        args.append("--k={}".format(k))
+        args.append("--permutations={}".format(permutations))
+        if threshold is not None: args.append("--threshold='{}'".format(threshold))
+        return self.run_tool('global_autocorrelation', args, callback) # returns 1 if error
+
     def greater_than(self, input1, input2, output, incl_equals=False, callback=None):
         """Performs a greater-than comparison operation on two rasters or a raster and a constant value.
 
@@ -8921,6 +10561,34 @@ Okay, that's it for now.
         if incl_equals: args.append("--incl_equals")
         return self.run_tool('greater_than', args, callback) # returns 1 if error
 
+    def hot_spot_analysis(self, i, field, output, statistic="gi_star", weights="distance", distance=None, k=8, alpha=0.05, fdr=False, callback=None):
+        """Performs a local spatial autocorrelation (Getis-Ord Gi* or Local Moran's I) analysis of a vector attribute.
+
+        Keyword arguments:
+
+        i -- Input vector file. 
+        field -- Numeric field to analyze. 
+        output -- Output vector file. 
+        statistic -- Local statistic; options are 'gi_star' and 'local_moran'. 
+        weights -- Method used to define neighbours; options are 'contiguity', 'distance' and 'knn'. 
+        distance -- Distance band used with distance weights. 
+        k -- Number of nearest neighbours used with knn weights. 
+        alpha -- Significance level used to categorize features. 
+        fdr -- Apply the Benjamini-Hochberg false discovery rate correction. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--field='{}'".format(field))
+        args.append("--output='{}'".format(output))
+        args.append("--statistic={}".format(statistic))
+        args.append("--weights={}".format(weights))
+        if distance is not None: args.append("--distance='{}'".format(distance))
+        args.append("--k={}".format(k))
+        args.append("--alpha={}".format(alpha))
+        if fdr: args.append("--fdr")
+        return self.run_tool('hot_spot_analysis', args, callback) # returns 1 if error
+
     def image_autocorrelation(self, inputs, output, contiguity="Rook", callback=None):
         """Performs Moran's I analysis on two or more input images.
 
@@ -8973,6 +10641,52 @@ Okay, that's it for now.
         args.append("--stat={}".format(stat))
         return self.run_tool('image_correlation_neighbourhood_analysis', args, callback) # returns 1 if error
 
+    def image_global_autocorrelation(self, i, output, contiguity="Rook", permutations=99, threshold=None, callback=None):
+        """Calculates Moran's I, Geary's C, and join count statistics for a raster, with permutation inference.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output HTML or JSON report file. 
+        contiguity -- Contiguity type. 
+        permutations -- Number of random permutations used to evaluate significance. 
+        threshold -- Values greater than or equal to the threshold are 'black' in the join count analysis; the default is the mean. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--contiguity={}".format(contiguity))
+        args.append("--permutations={}".format(permutations))
+        if threshold is not None: args.append("--threshold='{}'".format(threshold))
+        return self.run_tool('image_global_autocorrelation', args, callback) # returns 1 if error
+
+    def image_hot_spot_analysis(self, i, output, statistic="gi_star", filter=3, alpha=0.05, fdr=False, pvalue_output=None, category_output=None, callback=None):
+        """Performs a local spatial autocorrelation (Getis-Ord Gi* or Local Moran's I) analysis of a raster image.
+
+        Keyword arguments:
+
+        i -- Input raster file. 
+        output -- Output z-score raster file. 
+        statistic -- Local statistic; options are 'gi_star' and 'local_moran'. 
+        filter -- Size of the focal window, in grid cells. 
+        alpha -- Significance level used to categorize grid cells. 
+        fdr -- Apply the Benjamini-Hochberg false discovery rate correction. 
+        pvalue_output -- Optional output p-value raster file. 
+        category_output -- Optional output cluster/outlier category raster file. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--output='{}'".format(output))
+        args.append("--statistic={}".format(statistic))
+        args.append("--filter={}".format(filter))
+        args.append("--alpha={}".format(alpha))
+        if fdr: args.append("--fdr")
+        if pvalue_output is not None: args.append("--pvalue_output='{}'".format(pvalue_output))
+        if category_output is not None: args.append("--category_output='{}'".format(category_output))
+        return self.run_tool('image_hot_spot_analysis', args, callback) # returns 1 if error
+
     def image_regression(self, input1, input2, output, out_residuals=None, standardize=False, scattergram=False, num_samples=1000, callback=None):
         """Performs image regression analysis on two input images.
 
@@ -9819,6 +11533,88 @@ Okay, that's it for now.
     # Precision Agriculture #
     #########################
 
+    def clean_yield_data(self, i, yield_field, output, speed_field=None, moisture_field=None, pass_field=None, out_html=None, width=6.096, flow_delay=0.0, log_interval=1.0, max_change_in_heading=25.0, min_speed=None, max_speed=None, min_moisture=None, max_moisture=None, min_yield=0.0, max_yield=99999.9, min_eff_width=0.5, radius=None, z_threshold=2.5, keep_all=False, callback=None):
+        """Cleans yield monitor points with flow delay, filtering, overlap correction, and outlier removal.
+
+        Keyword arguments:
+
+        i -- Input yield monitor points vector file. 
+        yield_field -- Name of the attribute containing yield data. 
+        speed_field -- Name of the attribute containing ground speed data. 
+        moisture_field -- Name of the attribute containing grain moisture data. 
+        pass_field -- Name of the attribute containing pass line IDs. 
+        output -- Output cleaned points vector file. 
+        out_html -- Output HTML cleaning report file. 
+        width -- Combine header width, in map units. 
+        flow_delay -- Grain flow delay, in seconds. 
+        log_interval -- Time between logged points, in seconds. 
+        max_change_in_heading -- Max change in heading, in degrees, used to detect pass boundaries. 
+        min_speed -- Minimum ground speed. 
+        max_speed -- Maximum ground speed. 
+        min_moisture -- Minimum grain moisture. 
+        max_moisture -- Maximum grain moisture. 
+        min_yield -- Minimum yield value. 
+        max_yield -- Maximum yield value. 
+        min_eff_width -- Minimum fraction of the header width that must be unharvested ground. 
+        radius -- Outlier detection search radius, in map units; defaults to four header widths. 
+        z_threshold -- Robust z-score threshold used to identify local outliers. 
+        keep_all -- Output removed points, flagged with the step that removed them?. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--input='{}'".format(i))
+        args.append("--yield_field='{}'".format(yield_field))
+        if speed_field is not None: args.append("--speed_field='{}'".format(speed_field))
+        if moisture_field is not None: args.append("--moisture_field='{}'".format(moisture_field))
+        if pass_field is not None: args.append("--pass_field='{}'".format(pass_field))
+        args.append("--output='{}'".format(output))
+        if out_html is not None: args.append("--out_html='{}'".format(out_html))
+        args.append("--width={}".format(width))
+        args.append("--flow_delay={}".format(flow_delay))
+        args.append("--log_interval={}".format(log_interval))
+        args.append("--max_change_in_heading={}".format(max_change_in_heading))
+        if min_speed is not None: args.append("--min_speed='{}'".format(min_speed))
+        if max_speed is not None: args.append("--max_speed='{}'".format(max_speed))
+        if min_moisture is not None: args.append("--min_moisture='{}'".format(min_moisture))
+        if max_moisture is not None: args.append("--max_moisture='{}'".format(max_moisture))
+        args.append("--min_yield={}".format(min_yield))
+        args.append("--max_yield={}".format(max_yield))
+        args.append("--min_eff_width={}".format(min_eff_width))
+        if radius is not None: args.append("--radius='{}'".format(radius))
+        args.append("--z_threshold={}".format(z_threshold))
+        if keep_all: args.append("--keep_all")
+        return self.run_tool('clean_yield_data', args, callback) # returns 1 if error
+
+    def management_zones(self, inputs, output, out_polys=None, out_confusion=None, num_zones=3, fuzziness=1.3, max_iterations=300, tolerance=0.0001, filter=3, min_area=0.0, callback=None):
+        """Delineates management zones from multiple input rasters using fuzzy k-means clustering.
+
+        Keyword arguments:
+
+        inputs -- Input raster files (e.g. yield, EC, NDVI, slope). 
+        output -- Output management zones raster file. 
+        out_polys -- Optional output zone polygons vector file, with per-zone statistics. 
+        out_confusion -- Optional output confusion index raster file. 
+        num_zones -- Number of management zones. 
+        fuzziness -- Fuzziness exponent (m), greater than 1.0. 
+        max_iterations -- Maximum number of iterations. 
+        tolerance -- Maximum change in membership between iterations before completion. 
+        filter -- Size of the majority filter used to smooth zones; less than 3 for no smoothing. 
+        min_area -- Minimum area of contiguous zone patches, in map units squared. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--inputs='{}'".format(inputs))
+        args.append("--output='{}'".format(output))
+        if out_polys is not None: args.append("--out_polys='{}'".format(out_polys))
+        if out_confusion is not None: args.append("--out_confusion='{}'".format(out_confusion))
+        args.append("--num_zones={}".format(num_zones))
+        args.append("--fuzziness={}".format(fuzziness))
+        args.append("--max_iterations={}".format(max_iterations))
+        args.append("--tolerance={}".format(tolerance))
+        args.append("--filter={}".format(filter))
+        args.append("--min_area={}".format(min_area))
+        return self.run_tool('management_zones', args, callback) # returns 1 if error
+
     def reconcile_multiple_headers(self, i, region_field, yield_field, output, radius=None, min_yield=None, max_yield=None, mean_tonnage=None, callback=None):
         """This tool adjusts the crop yield values for data sets collected with multiple headers or combines.
 
@@ -9961,6 +11757,30 @@ Okay, that's it for now.
     # Stream Network Analysis #
     ###########################
 
+    def conform_streams_to_hydrography(self, streams, hydrography, output, distance=None, pour_pts=None, out_pour_pts=None, snap_dist=None, callback=None):
+        """Adjusts a raster stream network to follow mapped vector hydrography within a search corridor.
+
+        Keyword arguments:
+
+        streams -- Input raster streams file. 
+        hydrography -- Input vector stream lines file. 
+        output -- Output raster streams file. 
+        distance -- Search corridor distance, in map units; defaults to five grid cells. 
+        pour_pts -- Input vector pour points file to relocate to the corrected network. 
+        out_pour_pts -- Output vector file of relocated pour points. 
+        snap_dist -- Maximum pour point relocation distance, in map units; defaults to the corridor distance. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--streams='{}'".format(streams))
+        args.append("--hydrography='{}'".format(hydrography))
+        args.append("--output='{}'".format(output))
+        if distance is not None: args.append("--distance='{}'".format(distance))
+        if pour_pts is not None: args.append("--pour_pts='{}'".format(pour_pts))
+        if out_pour_pts is not None: args.append("--out_pour_pts='{}'".format(out_pour_pts))
+        if snap_dist is not None: args.append("--snap_dist='{}'".format(snap_dist))
+        return self.run_tool('conform_streams_to_hydrography', args, callback) # returns 1 if error
+
     def distance_to_outlet(self, d8_pntr, streams, output, esri_pntr=False, zero_background=False, callback=None):
         """Calculates the distance of stream grid cells to the channel network outlet cell.
 
@@ -9981,6 +11801,32 @@ Okay, that's it for now.
         if zero_background: args.append("--zero_background")
         return self.run_tool('distance_to_outlet', args, callback) # returns 1 if error
 
+    def drainage_density(self, d8_pntr, streams, output, watersheds=None, out_sources=None, out_basins=None, filter=101, esri_pntr=False, callback=None):
+        """Calculates drainage density and channel-head (source) density within a moving window or watersheds.
+
+        Keyword arguments:
+
+        d8_pntr -- Input raster D8 pointer file. 
+        streams -- Input raster streams file. 
+        watersheds -- Input vector watershed polygons file; if unspecified, a moving window is used. 
+        output -- Output drainage density raster file. 
+        out_sources -- Output channel-head (source) density raster file. 
+        out_basins -- Output vector watersheds file with per-basin density attributes. 
+        filter -- Size of the moving window, in grid cells; ignored when watersheds are specified. 
+        esri_pntr -- D8 pointer uses the ESRI style scheme. 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--d8_pntr='{}'".format(d8_pntr))
+        args.append("--streams='{}'".format(streams))
+        if watersheds is not None: args.append("--watersheds='{}'".format(watersheds))
+        args.append("--output='{}'".format(output))
+        if out_sources is not None: args.append("--out_sources='{}'".format(out_sources))
+        if out_basins is not None: args.append("--out_basins='{}'".format(out_basins))
+        args.append("--filter={}".format(filter))
+        if esri_pntr: args.append("--esri_pntr")
+        return self.run_tool('drainage_density', args, callback) # returns 1 if error
+
     def extract_streams(self, flow_accum, output, threshold, zero_background=False, callback=None):
         """Extracts stream grid cells from a flow accumulation raster.
 
@@ -10377,6 +12223,42 @@ Okay, that's it for now.
         if zero_background: args.append("--zero_background")
         return self.run_tool('stream_slope_continuous', args, callback) # returns 1 if error
 
+    def stream_solar_exposure(self, streams, dem, canopy, output, lat=None, long=None, start_day=152, end_day=243, time_step=30, az_fraction=5.0, max_dist=100.0, spacing=None, transmissivity=0.7, callback=None):
+        """Calculates the shaded fraction and incident direct solar energy of stream reaches.
+
+        Keyword arguments:
+
+        streams -- Input vector streams file. 
+        dem -- Input raster bare-earth DEM file. 
+        canopy -- Input raster canopy height model file. 
+        output -- Output vector streams file. 
+        lat -- Centre point latitude. 
+        long -- Centre point longitude. 
+        start_day -- Start day of the year (1-365). 
+        end_day -- End day of the year (1-365). 
+        time_step -- Time step of the solar almanac, in minutes. 
+        az_fraction -- Azimuth sector size, in degrees. 
+        max_dist -- Maximum horizon search distance, in map units. 
+        spacing -- Spacing of reach samples, in map units; defaults to the grid resolution. 
+        transmissivity -- Clear-sky atmospheric transmissivity (0-1). 
+        callback -- Custom function for handling tool text outputs.
+        """
+        args = []
+        args.append("--streams='{}'".format(streams))
+        args.append("--dem='{}'".format(dem))
+        args.append("--canopy='{}'".format(canopy))
+        args.append("--output='{}'".format(output))
+        if lat is not None: args.append("--lat='{}'".format(lat))
+        if long is not None: args.append("--long='{}'".format(long))
+        args.append("--start_day={}".format(start_day))
+        args.append("--end_day={}".format(end_day))
+        args.append("--time_step={}".format(time_step))
+        args.append("--az_fraction={}".format(az_fraction))
+        args.append("--max_dist={}".format(max_dist))
+        if spacing is not None: args.append("--spacing='{}'".format(spacing))
+        args.append("--transmissivity={}".format(transmissivity))
+        return self.run_tool('stream_solar_exposure', args, callback) # returns 1 if error
+
     def topological_stream_order(self, d8_pntr, streams, output, esri_pntr=False, zero_background=False, callback=None):
         """Assigns each link in a stream network its topological order.
 